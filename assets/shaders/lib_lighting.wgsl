@@ -0,0 +1,42 @@
+// Lighting Library Module
+// Phong lighting terms shared by the lighting examples
+//
+// Usage: #import playground::lighting
+
+#define_import_path playground::lighting
+#import playground::math
+
+// Diffuse term (Lambert's cosine law)
+fn lambert(normal: vec3<f32>, light_dir: vec3<f32>) -> f32 {
+    return max(dot(normal, light_dir), 0.0);
+}
+
+// Specular term of the Phong model
+fn phong_specular(normal: vec3<f32>, light_dir: vec3<f32>, view_dir: vec3<f32>, shininess: f32) -> f32 {
+    let reflect_dir = reflect(-light_dir, normal);
+    return pow(max(dot(view_dir, reflect_dir), 0.0), shininess);
+}
+
+// Schlick's approximation of the Fresnel factor
+fn fresnel_schlick(cos_theta: f32, f0: vec3<f32>) -> vec3<f32> {
+    return f0 + (1.0 - f0) * pow(1.0 - math::saturate(cos_theta), 5.0);
+}
+
+// Phong lighting with 10% ambient, shininess 32 and 50% specular
+// Shadow darkens diffuse and specular only (1.0 = fully lit)
+fn phong(
+    normal: vec3<f32>,
+    light_dir: vec3<f32>,
+    view_dir: vec3<f32>,
+    light_color: vec3<f32>,
+    shadow: f32
+) -> vec3<f32> {
+    let n = normalize(normal);
+    let l = normalize(light_dir);
+
+    let ambient = 0.1 * light_color;
+    let diffuse = lambert(n, l) * light_color;
+    let specular = 0.5 * phong_specular(n, l, view_dir, 32.0) * light_color;
+
+    return ambient + shadow * (diffuse + specular);
+}
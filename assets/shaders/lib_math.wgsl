@@ -0,0 +1,30 @@
+// Math Library Module
+// Constants and helpers shared by other shaders
+//
+// Usage: #import playground::math
+
+#define_import_path playground::math
+
+const PI: f32 = 3.14159265358979;
+const TAU: f32 = 6.28318530717959;
+
+// Clamp a value to [0, 1]
+fn saturate(x: f32) -> f32 {
+    return clamp(x, 0.0, 1.0);
+}
+
+// Map a value from one range to another
+fn remap(x: f32, from_min: f32, from_max: f32, to_min: f32, to_max: f32) -> f32 {
+    return to_min + (x - from_min) * (to_max - to_min) / (from_max - from_min);
+}
+
+// Rotation around the Y axis by an angle in radians
+fn rotation_y(angle: f32) -> mat3x3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return mat3x3<f32>(
+        vec3<f32>(c, 0.0, -s),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(s, 0.0, c)
+    );
+}
@@ -1,6 +1,8 @@
 // Main Pass Shader
 // Renders the scene with Phong lighting and shadow mapping
 
+#import playground::lighting
+
 // Bind Group 0: Camera
 struct CameraUniforms {
     view_proj: mat4x4<f32>,
@@ -96,7 +98,7 @@ fn calculate_shadow(shadow_pos: vec4<f32>) -> f32 {
     return shadow;
 }
 
-// Phong lighting model
+// Phong lighting from the shared lighting module, scaled by intensity
 fn calculate_phong_lighting(
     world_pos: vec3<f32>,
     world_normal: vec3<f32>,
@@ -106,26 +108,7 @@ fn calculate_phong_lighting(
     light_intensity: f32,
     shadow: f32
 ) -> vec3<f32> {
-    let normal = normalize(world_normal);
-    let light_direction = normalize(light_dir);
-    
-    // Ambient lighting (10% contribution, always present)
-    let ambient_strength = 0.1;
-    let ambient = ambient_strength * light_color;
-    
-    // Diffuse lighting (Lambert's cosine law)
-    let diff = max(dot(normal, light_direction), 0.0);
-    let diffuse = diff * light_color;
-    
-    // Specular lighting (Phong model)
-    // Shininess of 32 creates moderately shiny surface
-    let reflect_dir = reflect(-light_direction, normal);
-    let spec = pow(max(dot(view_dir, reflect_dir), 0.0), 32.0);
-    let specular_strength = 0.5; // 50% specular contribution
-    let specular = specular_strength * spec * light_color;
-    
-    // Combine with shadow (ambient is not affected by shadows)
-    return (ambient + shadow * (diffuse + specular)) * light_intensity;
+    return lighting::phong(world_normal, light_dir, view_dir, light_color, shadow) * light_intensity;
 }
 
 @fragment
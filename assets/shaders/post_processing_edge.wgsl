@@ -1,29 +1,7 @@
 // Edge detection post-processing shader
 // Applies Sobel edge detection to the input texture
 
-struct VertexInput {
-    @location(0) position: vec2<f32>,
-    @location(1) tex_coords: vec2<f32>,
-}
-
-struct VertexOutput {
-    @builtin(position) position: vec4<f32>,
-    @location(0) tex_coords: vec2<f32>,
-}
-
-@group(0) @binding(0)
-var texture_sampler: sampler;
-
-@group(0) @binding(1)
-var input_texture: texture_2d<f32>;
-
-@vertex
-fn vs_main(input: VertexInput) -> VertexOutput {
-    var output: VertexOutput;
-    output.position = vec4<f32>(input.position, 0.0, 1.0);
-    output.tex_coords = input.tex_coords;
-    return output;
-}
+#include "post_processing_common.wgsl"
 
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
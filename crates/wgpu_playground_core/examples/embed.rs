@@ -0,0 +1,344 @@
+/// Embedding playground panels in another egui/wgpu application
+///
+/// This host owns its own window, device and egui integration and knows
+/// nothing about the playground GUI. It draws a spinning clear color as
+/// "the application" and opens playground panels as debugging windows via
+/// the `wgpu_playground_core::embed` API.
+///
+/// Run with: cargo run -p wgpu_playground_core --example embed
+use egui_wgpu::ScreenDescriptor;
+use std::sync::Arc;
+use wgpu_playground_core::embed::{
+    self, BufferInspector, ConsolePanel, EmbedContext, EmbeddedPanel, ShaderEditor,
+    ShaderTranslationPanel, TextureInspector, TexturePanel,
+};
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    window::{Window, WindowId},
+};
+
+/// An embedded panel plus its window visibility
+struct DebugWindow<P> {
+    panel: P,
+    open: bool,
+}
+
+impl<P: EmbeddedPanel> DebugWindow<P> {
+    fn new(panel: P, open: bool) -> Self {
+        Self { panel, open }
+    }
+
+    fn toggle_button(&mut self, ui: &mut egui::Ui) {
+        ui.toggle_value(&mut self.open, self.panel.title());
+    }
+
+    fn show(&mut self, ctx: &egui::Context, embed_ctx: &mut EmbedContext<'_>) {
+        embed::show_window(ctx, &mut self.panel, &mut self.open, embed_ctx);
+    }
+}
+
+/// The host's debugging UI, built only from `embed` types
+struct DebugUi {
+    shader_editor: DebugWindow<ShaderEditor>,
+    shader_translation: DebugWindow<ShaderTranslationPanel>,
+    texture_panel: DebugWindow<TexturePanel>,
+    texture_inspector: DebugWindow<TextureInspector>,
+    buffer_inspector: DebugWindow<BufferInspector>,
+    console: DebugWindow<ConsolePanel>,
+}
+
+impl DebugUi {
+    fn new() -> Self {
+        let mut console = ConsolePanel::new();
+        console.info("Host application started with embedded playground panels");
+
+        let mut buffer_inspector = BufferInspector::new();
+        // Show the host's own data, e.g. the clear color it renders with
+        buffer_inspector.load_data(bytemuck::cast_slice(&[0.1f32, 0.2, 0.3, 1.0]).to_vec());
+
+        Self {
+            shader_editor: DebugWindow::new(ShaderEditor::new(), true),
+            shader_translation: DebugWindow::new(ShaderTranslationPanel::new(), false),
+            texture_panel: DebugWindow::new(TexturePanel::new(), false),
+            texture_inspector: DebugWindow::new(TextureInspector::new(), false),
+            buffer_inspector: DebugWindow::new(buffer_inspector, false),
+            console: DebugWindow::new(console, true),
+        }
+    }
+
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label("Debug windows:");
+            self.shader_editor.toggle_button(ui);
+            self.shader_translation.toggle_button(ui);
+            self.texture_panel.toggle_button(ui);
+            self.texture_inspector.toggle_button(ui);
+            self.buffer_inspector.toggle_button(ui);
+            self.console.toggle_button(ui);
+        });
+
+        let ctx = ui.ctx().clone();
+        let mut embed_ctx = EmbedContext::new(device, queue).with_renderer(renderer);
+        self.shader_editor.show(&ctx, &mut embed_ctx);
+        self.shader_translation.show(&ctx, &mut embed_ctx);
+        self.texture_panel.show(&ctx, &mut embed_ctx);
+        self.texture_inspector.show(&ctx, &mut embed_ctx);
+        self.buffer_inspector.show(&ctx, &mut embed_ctx);
+        self.console.show(&ctx, &mut embed_ctx);
+    }
+}
+
+struct HostState {
+    window: Arc<Window>,
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface_config: wgpu::SurfaceConfiguration,
+    egui_renderer: egui_wgpu::Renderer,
+    egui_state: egui_winit::State,
+    egui_ctx: egui::Context,
+    debug_ui: DebugUi,
+    start: std::time::Instant,
+}
+
+impl HostState {
+    async fn new(window: Arc<Window>) -> Self {
+        let size = window.inner_size();
+        let instance = wgpu::Instance::default();
+        let surface = instance
+            .create_surface(window.clone())
+            .expect("Failed to create surface");
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                compatible_surface: Some(&surface),
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to find a GPU adapter");
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("Embed Host Device"),
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to create device");
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = surface_caps
+            .formats
+            .iter()
+            .copied()
+            .find(|f| f.is_srgb())
+            .unwrap_or(surface_caps.formats[0]);
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&device, &surface_config);
+
+        let egui_ctx = egui::Context::default();
+        let egui_state = egui_winit::State::new(
+            egui_ctx.clone(),
+            egui::ViewportId::ROOT,
+            &window,
+            None,
+            None,
+            None,
+        );
+        let egui_renderer = egui_wgpu::Renderer::new(
+            &device,
+            surface_config.format,
+            egui_wgpu::RendererOptions::default(),
+        );
+
+        Self {
+            window,
+            surface,
+            device,
+            queue,
+            surface_config,
+            egui_renderer,
+            egui_state,
+            egui_ctx,
+            debug_ui: DebugUi::new(),
+            start: std::time::Instant::now(),
+        }
+    }
+
+    fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
+        if new_size.width > 0 && new_size.height > 0 {
+            self.surface_config.width = new_size.width;
+            self.surface_config.height = new_size.height;
+            self.surface.configure(&self.device, &self.surface_config);
+        }
+    }
+
+    fn render(&mut self) {
+        let surface_texture = match self.surface.get_current_texture() {
+            wgpu::CurrentSurfaceTexture::Success(t)
+            | wgpu::CurrentSurfaceTexture::Suboptimal(t) => t,
+            wgpu::CurrentSurfaceTexture::Outdated | wgpu::CurrentSurfaceTexture::Lost => {
+                self.resize(self.window.inner_size());
+                return;
+            }
+            _ => return,
+        };
+        let view = surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Embed Host Encoder"),
+            });
+
+        // The host application's own rendering
+        let t = self.start.elapsed().as_secs_f64();
+        {
+            let _pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Host Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1 + 0.1 * t.sin().abs(),
+                            g: 0.1,
+                            b: 0.2 + 0.1 * t.cos().abs(),
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+        }
+
+        // Embedded playground panels on top
+        let raw_input = self.egui_state.take_egui_input(&self.window);
+        let egui_output = self.egui_ctx.run_ui(raw_input, |ui| {
+            self.debug_ui
+                .ui(ui, &self.device, &self.queue, &mut self.egui_renderer);
+        });
+        self.egui_state
+            .handle_platform_output(&self.window, egui_output.platform_output);
+
+        let clipped_primitives = self
+            .egui_ctx
+            .tessellate(egui_output.shapes, egui_output.pixels_per_point);
+        let screen_descriptor = ScreenDescriptor {
+            size_in_pixels: [self.surface_config.width, self.surface_config.height],
+            pixels_per_point: self.window.scale_factor() as f32,
+        };
+        for (id, image_delta) in &egui_output.textures_delta.set {
+            self.egui_renderer
+                .update_texture(&self.device, &self.queue, *id, image_delta);
+        }
+        self.egui_renderer.update_buffers(
+            &self.device,
+            &self.queue,
+            &mut encoder,
+            &clipped_primitives,
+            &screen_descriptor,
+        );
+        {
+            let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Embedded UI Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            let mut render_pass = render_pass.forget_lifetime();
+            self.egui_renderer
+                .render(&mut render_pass, &clipped_primitives, &screen_descriptor);
+        }
+        for id in &egui_output.textures_delta.free {
+            self.egui_renderer.free_texture(id);
+        }
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+        surface_texture.present();
+    }
+}
+
+struct Host {
+    state: Option<HostState>,
+}
+
+impl ApplicationHandler for Host {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.state.is_none() {
+            let window_attributes = Window::default_attributes()
+                .with_title("Host Application (embedded playground panels)")
+                .with_inner_size(winit::dpi::LogicalSize::new(1280, 720));
+            let window = Arc::new(
+                event_loop
+                    .create_window(window_attributes)
+                    .expect("Failed to create window"),
+            );
+            self.state = Some(pollster::block_on(HostState::new(window)));
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        let Some(state) = &mut self.state else {
+            return;
+        };
+        if state
+            .egui_state
+            .on_window_event(&state.window, &event)
+            .consumed
+        {
+            return;
+        }
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::Resized(size) => state.resize(size),
+            WindowEvent::RedrawRequested => state.render(),
+            _ => {}
+        }
+    }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(state) = &self.state {
+            state.window.request_redraw();
+        }
+    }
+}
+
+fn main() {
+    env_logger::init();
+    let event_loop = EventLoop::new().expect("Failed to create event loop");
+    event_loop.set_control_flow(ControlFlow::Poll);
+    event_loop
+        .run_app(&mut Host { state: None })
+        .expect("Event loop error");
+}
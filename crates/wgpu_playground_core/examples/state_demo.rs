@@ -13,6 +13,8 @@ fn main() {
     let state = PlaygroundState {
         version: "1.0".to_string(),
         theme: wgpu_playground_core::state::Theme::default(),
+        language: None,
+        custom_theme: None,
         buffer_panel: Some(BufferPanelState {
             label: "vertex_buffer".to_string(),
             size: "4096".to_string(),
@@ -42,6 +44,7 @@ fn main() {
             usage_texture_binding: true,
             usage_storage_binding: false,
             usage_render_attachment: true,
+            image_data: None,
         }),
         sampler_panel: Some(SamplerPanelState {
             label: "linear_sampler".to_string(),
@@ -75,6 +78,7 @@ fn fs_main() -> @location(0) vec4<f32> {
             .to_string(),
             label: "triangle_shader".to_string(),
             file_path: "".to_string(),
+            ..Default::default()
         }),
         render_pipeline_panel: None,
         compute_pipeline_panel: None,
@@ -83,6 +87,7 @@ fn fs_main() -> @location(0) vec4<f32> {
         api_coverage: None,
         tutorial_state: None,
         learning_progress: None,
+        device_config: None,
     };
     println!("   ✓ State created with:");
     println!("     - Buffer: vertex_buffer (4096 bytes, VERTEX | COPY_DST)");
@@ -0,0 +1,431 @@
+# German messages of the wgpu playground GUI.
+#
+# WebGPU API names (buffer usages, load/store operations, ...) are left in
+# English so that they match the specification and the code.
+#
+# The editor panels (buffers, textures, samplers, bind groups and pipelines)
+# are not translated yet and are shown in English.
+
+language-name = Deutsch
+
+## Tooltips
+
+tooltip-spec-link = WebGPU-Spezifikation
+tooltip-spec-link-hover = Klicken, um die Spezifikation zu öffnen
+
+# Buffer usage flag tooltips
+tooltip-buffer-usage-vertex = Der Buffer kann in Draw-Befehlen als Vertex-Buffer verwendet werden. Er enthält Attribute pro Vertex wie Position, Normale oder UV-Koordinaten.
+tooltip-buffer-usage-index = Der Buffer kann in indizierten Draw-Befehlen als Index-Buffer verwendet werden. Er enthält Indizes, die auf Vertices in den Vertex-Buffern verweisen.
+tooltip-buffer-usage-uniform = Der Buffer kann als Uniform-Buffer verwendet werden. Er enthält schreibgeschützte Daten für Shader, typischerweise globale Parameter wie Transformationsmatrizen.
+tooltip-buffer-usage-storage = Der Buffer kann als Storage-Buffer verwendet werden. Shader können beliebige Daten lesen und schreiben, nützlich für Compute-Operationen und große Datensätze.
+tooltip-buffer-usage-indirect = Der Buffer kann für indirekte Draw-Befehle verwendet werden. Er enthält Draw-Parameter, die dynamisch auf der GPU bestimmt werden können.
+tooltip-buffer-usage-copy-src = Der Buffer kann als Quelle einer Kopieroperation verwendet werden. Seine Daten können in einen anderen Buffer oder eine Textur kopiert werden.
+tooltip-buffer-usage-copy-dst = Der Buffer kann als Ziel einer Kopieroperation verwendet werden. Daten können aus einem anderen Buffer, einer Textur oder von der CPU hineingeschrieben werden.
+tooltip-buffer-usage-map-read = Der Buffer kann zum Lesen auf der CPU gemappt werden. Damit lassen sich GPU-Daten zurücklesen; nicht mit MAP_WRITE kombinierbar.
+tooltip-buffer-usage-map-write = Der Buffer kann zum Schreiben von der CPU gemappt werden. Damit lassen sich Daten auf die GPU hochladen; nicht mit MAP_READ kombinierbar.
+tooltip-buffer-usage-query-resolve = Der Buffer kann Query-Ergebnisse aufnehmen. Er speichert Query-Daten wie Zeitstempel oder Ergebnisse von Occlusion-Tests.
+
+# Texture usage flag tooltips
+tooltip-texture-usage-copy-src = Die Textur kann als Quelle einer Kopieroperation verwendet werden. Ihre Daten können in eine andere Textur oder einen Buffer kopiert werden.
+tooltip-texture-usage-copy-dst = Die Textur kann als Ziel einer Kopieroperation verwendet werden. Daten können aus einem Buffer, einer anderen Textur oder aus Bildern hineingeschrieben werden.
+tooltip-texture-usage-texture-binding = Die Textur kann zum Sampeln an Shader gebunden werden. Shader lesen und sampeln sie über Sampler.
+tooltip-texture-usage-storage-binding = Die Textur kann als Storage-Textur gebunden werden. Shader lesen und schreiben Texturdaten direkt ohne Sampling, nützlich für Compute-Operationen.
+tooltip-texture-usage-render-attachment = Die Textur kann als Render-Ziel verwendet werden. Render-Operationen schreiben als Farb-, Tiefen- oder Stencil-Attachment in sie.
+
+# Primitive topology tooltips
+tooltip-primitive-topology-point-list = Jeder Vertex ist ein Punkt. Nützlich für Partikelsysteme und Punktwolken.
+tooltip-primitive-topology-line-list = Je zwei Vertices bilden eine eigene Linie. Nützlich für Drahtgitter und Debug-Visualisierungen.
+tooltip-primitive-topology-line-strip = Die Vertices bilden eine zusammenhängende Linienfolge. Jeder Vertex wird mit dem nächsten verbunden, sodass ein Polygonzug entsteht.
+tooltip-primitive-topology-triangle-list = Je drei Vertices bilden ein eigenes Dreieck. Die häufigste Topologie für 3D-Rendering.
+tooltip-primitive-topology-triangle-strip = Die Vertices bilden einen zusammenhängenden Dreiecksstreifen. Speichersparender für manche Meshes wie Gitter.
+
+# Cull mode tooltips
+tooltip-cull-mode-none = Kein Face Culling. Vorder- und Rückseiten werden gerendert. Nützlich für 2D-Objekte oder doppelseitige Geometrie.
+tooltip-cull-mode-front = Vorderseiten werden verworfen, nur Rückseiten werden gerendert. In der Praxis selten.
+tooltip-cull-mode-back = Rückseiten werden verworfen, nur Vorderseiten werden gerendert. Der übliche Modus für 3D-Objekte.
+
+# Polygon mode tooltips
+tooltip-polygon-mode-fill = Dreiecke werden gefüllt. Der einzige in WebGPU verfügbare Modus.
+tooltip-polygon-mode-line = Nur die Kanten jedes Dreiecks werden gezeichnet, als echtes Drahtgitter des Meshes. Benötigt das native Feature POLYGON_MODE_LINE.
+tooltip-polygon-mode-point = Nur die Eckpunkte jedes Dreiecks werden als Punkte gezeichnet. Benötigt das native Feature POLYGON_MODE_POINT.
+
+# Front face winding order tooltips
+tooltip-front-face-ccw = Gegen den Uhrzeigersinn angeordnete Vertices (von der Kamera aus gesehen) bilden die Vorderseite. Dies ist die übliche Konvention.
+tooltip-front-face-cw = Im Uhrzeigersinn angeordnete Vertices (von der Kamera aus gesehen) bilden die Vorderseite.
+
+# Compare function tooltips (for depth/stencil testing)
+tooltip-compare-function-never = Der Vergleich schlägt immer fehl. Kein Fragment besteht den Test.
+tooltip-compare-function-less = Besteht, wenn der neue Wert kleiner als der gespeicherte ist. Am häufigsten beim Tiefentest.
+tooltip-compare-function-equal = Besteht, wenn der neue Wert gleich dem gespeicherten ist.
+tooltip-compare-function-less-equal = Besteht, wenn der neue Wert kleiner oder gleich dem gespeicherten ist.
+tooltip-compare-function-greater = Besteht, wenn der neue Wert größer als der gespeicherte ist. Wird für Reverse-Z-Tiefentests verwendet.
+tooltip-compare-function-not-equal = Besteht, wenn der neue Wert ungleich dem gespeicherten ist.
+tooltip-compare-function-greater-equal = Besteht, wenn der neue Wert größer oder gleich dem gespeicherten ist.
+tooltip-compare-function-always = Der Vergleich besteht immer. Alle Fragmente bestehen den Test.
+
+# Blend factor tooltips
+tooltip-blend-factor-zero = Der Blend-Faktor ist 0. Die Quell- oder Zielfarbe wird mit null multipliziert.
+tooltip-blend-factor-one = Der Blend-Faktor ist 1. Die Quell- oder Zielfarbe wird unverändert verwendet.
+tooltip-blend-factor-src = Der Blend-Faktor ist die Quellfarbe.
+tooltip-blend-factor-one-minus-src = Der Blend-Faktor ist 1 minus die Quellfarbe.
+tooltip-blend-factor-src-alpha = Der Blend-Faktor ist das Quell-Alpha.
+tooltip-blend-factor-one-minus-src-alpha = Der Blend-Faktor ist 1 minus das Quell-Alpha. Üblich für Alpha-Blending.
+tooltip-blend-factor-dst = Der Blend-Faktor ist die Zielfarbe.
+tooltip-blend-factor-one-minus-dst = Der Blend-Faktor ist 1 minus die Zielfarbe.
+tooltip-blend-factor-dst-alpha = Der Blend-Faktor ist das Ziel-Alpha.
+tooltip-blend-factor-one-minus-dst-alpha = Der Blend-Faktor ist 1 minus das Ziel-Alpha.
+
+# Blend operation tooltips
+tooltip-blend-operation-add = Quell- und Zielfarbe werden addiert. Die häufigste Blend-Operation.
+tooltip-blend-operation-subtract = Die Zielfarbe wird von der Quellfarbe abgezogen.
+tooltip-blend-operation-reverse-subtract = Die Quellfarbe wird von der Zielfarbe abgezogen.
+tooltip-blend-operation-min = Das Minimum aus Quell- und Zielfarbe wird verwendet.
+tooltip-blend-operation-max = Das Maximum aus Quell- und Zielfarbe wird verwendet.
+
+# Sampler address mode tooltips
+tooltip-address-mode-clamp-to-edge = Texturkoordinaten werden auf [0, 1] begrenzt. Koordinaten außerhalb verwenden die Randfarbe.
+tooltip-address-mode-repeat = Texturkoordinaten laufen über. Die Textur wiederholt sich kachelartig.
+tooltip-address-mode-mirror-repeat = Texturkoordinaten laufen gespiegelt über. Die Textur wechselt bei jeder Wiederholung zwischen normal und gespiegelt.
+tooltip-address-mode-clamp-to-border = Texturkoordinaten außerhalb von [0, 1] verwenden die Rahmenfarbe.
+
+# Sampler filter mode tooltips
+tooltip-filter-mode-nearest = Nearest-Neighbor-Filterung. Schnell, kann aber pixelig wirken. Verwendet die Farbe des nächstgelegenen Texels.
+tooltip-filter-mode-linear = Lineare (bilineare) Filterung. Weicheres Ergebnis durch Interpolation zwischen benachbarten Texeln.
+
+# Sampler property tooltips
+tooltip-sampler-lod-min-clamp = Minimaler Detailgrad (LOD) beim Sampeln. Kleinere Werte verwenden höher aufgelöste Mipmap-Stufen. Üblicherweise 0.0 für die höchste Auflösung.
+tooltip-sampler-lod-max-clamp = Maximaler Detailgrad (LOD) beim Sampeln. Größere Werte erlauben niedriger aufgelöste Mipmap-Stufen. Üblich sind 32.0 oder die tatsächliche Anzahl der Mip-Stufen.
+tooltip-sampler-max-anisotropy = Maximale Anisotropie für anisotrope Filterung. Höhere Werte (bis 16) verbessern die Texturqualität bei flachen Blickwinkeln, können aber die Leistung senken. Der Wert 1 schaltet anisotrope Filterung ab.
+tooltip-sampler-border-color = Die Farbe, die bei Adressmodus ClampToBorder für Texturkoordinaten außerhalb von [0, 1] verwendet wird. Zur Wahl stehen transparentes Schwarz, deckendes Schwarz, deckendes Weiß und Null.
+
+# Stencil operation tooltips
+tooltip-stencil-operation-keep = Der aktuelle Stencil-Wert bleibt unverändert.
+tooltip-stencil-operation-zero = Der Stencil-Wert wird auf 0 gesetzt.
+tooltip-stencil-operation-replace = Der Stencil-Wert wird durch den Referenzwert ersetzt.
+tooltip-stencil-operation-invert = Der Stencil-Wert wird bitweise invertiert.
+tooltip-stencil-operation-increment-clamp = Der Stencil-Wert wird erhöht und beim Maximalwert begrenzt.
+tooltip-stencil-operation-decrement-clamp = Der Stencil-Wert wird verringert und bei 0 begrenzt.
+tooltip-stencil-operation-increment-wrap = Der Stencil-Wert wird erhöht und springt bei Überlauf auf 0.
+tooltip-stencil-operation-decrement-wrap = Der Stencil-Wert wird verringert und springt bei Unterlauf auf den Maximalwert.
+
+# General property tooltips
+tooltip-property-buffer-size = Größe des Buffers in Bytes. Muss größer als 0 sein und den Ausrichtungsanforderungen der Verwendung entsprechen.
+tooltip-property-buffer-mapped-at-creation = Ob der Buffer direkt nach dem Erstellen gemappt wird. Damit lassen sich Anfangsdaten ohne separate Map-Operation schreiben.
+tooltip-property-texture-width = Breite der Textur in Pixeln. Muss größer als 0 sein.
+tooltip-property-texture-height = Höhe der Textur in Pixeln. Muss größer als 0 sein.
+tooltip-property-texture-depth = Tiefe oder Anzahl der Array-Ebenen. Bei 2D-Texturen ist das die Anzahl der Ebenen, bei 3D-Texturen die Tiefe.
+tooltip-property-texture-mip-levels = Anzahl der Mip-Stufen. Mip-Stufen sind schrittweise verkleinerte Versionen der Textur, die die Darstellungsqualität bei unterschiedlichen Entfernungen verbessern.
+tooltip-property-texture-sample-count = Anzahl der Samples pro Pixel für Multisampling. Muss 1 (kein Multisampling) oder eine Zweierpotenz bis zum Maximum des Geräts sein.
+tooltip-property-depth-write-enabled = Ob Fragmente in den Tiefenpuffer schreiben. Meist für deckende Objekte aktiviert und für transparente deaktiviert.
+tooltip-property-alpha-to-coverage = Ob der Alphakanal beim Multisampling die Abdeckung bestimmt. Nützlich für Laub und transparente Texturen.
+tooltip-property-sample-count = Anzahl der Samples pro Pixel für Multisample-Antialiasing (MSAA). Höhere Werte verbessern die Qualität, benötigen aber mehr Speicher.
+
+# Shader visibility tooltips
+tooltip-shader-visibility-vertex = Die Ressource ist für Vertex-Shader sichtbar. Vertex-Shader verarbeiten jeden Vertex der Geometrie.
+tooltip-shader-visibility-fragment = Die Ressource ist für Fragment-Shader sichtbar. Fragment-Shader bestimmen die Farbe jedes Pixels.
+tooltip-shader-visibility-compute = Die Ressource ist für Compute-Shader sichtbar. Compute-Shader führen allgemeine Berechnungen auf der GPU aus.
+
+# Compute dispatch tooltips
+tooltip-compute-workgroup-count-x = Anzahl der Workgroups in X-Richtung. Jede Workgroup führt den Compute-Shader mit der im Shader festgelegten Workgroup-Größe aus. Aufrufe in X = workgroup_count_x * workgroup_size_x.
+tooltip-compute-workgroup-count-y = Anzahl der Workgroups in Y-Richtung. Jede Workgroup führt den Compute-Shader mit der im Shader festgelegten Workgroup-Größe aus. Aufrufe in Y = workgroup_count_y * workgroup_size_y.
+tooltip-compute-workgroup-count-z = Anzahl der Workgroups in Z-Richtung. Jede Workgroup führt den Compute-Shader mit der im Shader festgelegten Workgroup-Größe aus. Aufrufe in Z = workgroup_count_z * workgroup_size_z.
+tooltip-compute-entry-point = Name der Compute-Shader-Funktion, die als Einstiegspunkt dient. Sie muss in WGSL mit dem Attribut @compute markiert sein.
+tooltip-compute-pipeline-layout = Legt das Bind-Group-Layout der Compute-Pipeline fest. Es kann aus dem Shader erzeugt oder von Hand angegeben werden, um die Aufteilung der Bind Groups zu steuern.
+
+# Render pass load/store operation tooltips
+tooltip-load-store-op-load-op-clear = Das Attachment wird zu Beginn des Render-Passes auf einen Wert gelöscht. Der bisherige Inhalt wird verworfen und alle Pixel erhalten den Löschwert.
+tooltip-load-store-op-load-op-load = Der bisherige Inhalt des Attachments wird zu Beginn des Render-Passes geladen. Die Pixelwerte bleiben zum Weiterbearbeiten oder Blenden erhalten.
+tooltip-load-store-op-store-op-store = Die Ergebnisse des Render-Passes werden im Attachment gespeichert. Alle gerenderten Pixel werden für die spätere Verwendung in den Speicher geschrieben.
+tooltip-load-store-op-store-op-discard = Die Ergebnisse des Render-Passes werden verworfen. Der Inhalt des Attachments ist danach undefiniert. Nützlich für temporäres Rendering oder wenn die Ergebnisse nicht gebraucht werden.
+tooltip-load-store-op-color-attachment = Einstellungen des Farb-Attachments des Render-Passes. Legt die Zieltextur fest und wie ihr Inhalt zu Beginn und am Ende des Passes behandelt wird.
+tooltip-load-store-op-depth-stencil-attachment = Einstellungen des Depth-Stencil-Attachments des Render-Passes. Legt die Tiefen-/Stencil-Textur und die Operationen für Tiefen- und Stencil-Tests fest.
+
+# Draw command parameter tooltips
+tooltip-draw-vertex-count = Anzahl der zu zeichnenden Vertices. Bei Dreieckslisten meist ein Vielfaches von 3. Jeder Vertex wird vom Vertex-Shader verarbeitet.
+tooltip-draw-instance-count = Anzahl der zu zeichnenden Instanzen. Mehr als 1 aktiviert instanziertes Rendering, bei dem dieselbe Geometrie mehrfach mit unterschiedlichen Instanzdaten gezeichnet wird.
+tooltip-draw-first-vertex = Versatz des ersten zu zeichnenden Vertex im Vertex-Buffer. Damit lässt sich ein Teil der Vertices zeichnen, ohne eigene Buffer anzulegen.
+tooltip-draw-first-instance = Versatz der ersten zu zeichnenden Instanz. Beeinflusst den eingebauten Wert instance_index im Vertex-Shader, nützlich für den Zugriff auf Instanzdaten.
+
+## Navigation sidebar
+
+nav-heading = Navigation
+nav-quick = Schnellnavigation: { $modifier }+1-6
+nav-quick-shortcuts =
+    Tastenkürzel:
+    { $modifier }+1: Rendering
+    { $modifier }+2: Compute
+    { $modifier }+3: Buffer
+    { $modifier }+4: Texturen
+    { $modifier }+5: Konsole
+    { $modifier }+6: Einstellungen
+
+nav-setup = ⚙️ Einrichtung & Konfiguration
+    .tooltip = GPU-Adapter und Geräteeinstellungen konfigurieren
+nav-adapter-selection = Adapterauswahl
+    .tooltip = GPU-Adapter auswählen und konfigurieren
+nav-device-config = Gerätekonfiguration
+    .tooltip = WebGPU-Features aktivieren oder deaktivieren und Gerätelimits anpassen
+nav-surface-config = Surface-Konfiguration
+    .tooltip = Format, Present-Modus und Alpha-Modus der Fenster-Surface wechseln
+nav-device-info = Geräteinformationen
+    .tooltip = Ausführliche Informationen zum GPU-Adapter anzeigen
+
+nav-rendering = 🎨 Rendering & Grafik
+    .tooltip = Rendering-Beispiele und Pipeline-Konfiguration (Strg+1)
+nav-examples = Beispiele & Vorschau
+    .tooltip = Rendering-Beispiele und Live-Vorschau anzeigen
+nav-render-pipeline = Render-Pipeline
+    .tooltip = Render-Pipeline und Shader konfigurieren
+nav-render-pass = Render-Pass
+    .tooltip = Einstellungen des Render-Passes konfigurieren
+nav-render-targets = Render-Ziele
+    .tooltip = Offscreen-Ziele anlegen, in einem Render-Graph verbinden und untersuchen
+nav-draw-commands = Draw-Befehle
+    .tooltip = Draw-Befehle konfigurieren und ausführen
+nav-fragment-playground = Fragment-Spielwiese
+    .tooltip = Bildschirmfüllende Fragment-Shader im Stil von ShaderToy schreiben
+
+nav-compute = 🧮 Compute & ML
+    .tooltip = Compute-Shader-Operationen und ML-Inferenz (Strg+2)
+nav-compute-panel = Compute-Übersicht
+    .tooltip = Compute-Shader-Operationen erkunden
+nav-compute-pipeline = Compute-Pipeline
+    .tooltip = Compute-Pipeline und Shader konfigurieren
+nav-compute-dispatch = Compute-Dispatch
+    .tooltip = Compute-Operationen konfigurieren und ausführen
+nav-async-compute = Asynchrones Compute
+    .tooltip = Compute-Aufträge in Teilen einplanen und die Übermittlungslatenz beobachten
+nav-workgroup-tuner = Workgroup-Tuner
+    .tooltip = Einen Compute-Shader mit verschiedenen Workgroup-Größen vermessen
+
+nav-resources = 📦 Ressourcen
+    .tooltip = GPU-Ressourcen verwalten (Buffer, Texturen, Sampler)
+nav-buffers = Buffer
+    .tooltip = GPU-Buffer anlegen und konfigurieren (Strg+3)
+nav-textures = Texturen
+    .tooltip = Texturen aus Bildern laden und konfigurieren (Strg+4)
+nav-samplers = Sampler
+    .tooltip = Sampling-Modi für Texturen konfigurieren
+nav-models = 3D-Modelle
+    .tooltip = 3D-Modelle laden und anzeigen
+nav-bind-groups = Bind Groups
+    .tooltip = Bind Groups für Shader-Ressourcen anlegen
+nav-bind-group-layouts = Bind-Group-Layouts
+    .tooltip = Bind-Group-Layouts festlegen
+
+nav-tools = 🔧 Werkzeuge & Debugging
+    .tooltip = Debugging-Werkzeuge, Konsole und Leistungsüberwachung
+nav-tutorials = Tutorials
+    .tooltip = Interaktive Anleitungen zum Lernen von WebGPU
+nav-learning-path = Lernpfad
+    .tooltip = Visueller Lernpfad mit WebGPU-Konzepten und Fortschritt
+nav-presets = Konfigurationsvorlagen
+    .tooltip = Vorlagen für typische Rendering-Szenarien durchsuchen und laden
+nav-history = Verlauf
+    .tooltip = Rückgängig/Wiederholen-Verlauf durchsuchen und zu früheren Zuständen springen
+nav-resource-inspector = Ressourcen-Inspektor
+    .tooltip = Alle GPU-Ressourcen untersuchen
+nav-buffer-inspector = Buffer-Inspektor
+    .tooltip = Buffer-Inhalte anzeigen und untersuchen
+nav-texture-inspector = Textur-Inspektor
+    .tooltip = Texturdaten anzeigen und untersuchen
+nav-pipeline-debugger = Pipeline-Debugger
+    .tooltip = Render- und Compute-Pipelines debuggen
+nav-debug-counters = Debug-Zähler
+    .tooltip = Benannte atomare Zähler, die ein Shader erhöht, als Diagramm anzeigen
+nav-shader-variants = Shader-Varianten
+    .tooltip = Permutationen eines Ubershaders kompilieren und vergleichen
+nav-shader-translation = Shader-Übersetzung
+    .tooltip = Das aus dem aktuellen Shader erzeugte SPIR-V, HLSL, MSL und GLSL anzeigen
+nav-external-comparison = Externer Vergleich
+    .tooltip = Die Ausgabe mit Bildern anderer Implementierungen vergleichen
+nav-exercise-checks = Übungsprüfungen
+    .tooltip = Die Prüfungen einer Übung zu Zustand und gerenderter Ausgabe beim Bearbeiten auswerten
+nav-command-recording = Befehlsaufzeichnung
+    .tooltip = GPU-Befehle aufzeichnen und wiedergeben
+nav-api-coverage = API-Abdeckung
+    .tooltip = Die Nutzung der WebGPU-API verfolgen und visualisieren
+nav-api-reference = API-Referenz
+    .tooltip = WebGPU-API-Dokumentation und Beispiele durchsuchen
+nav-console = Konsole
+    .tooltip = GPU-Fehler und Validierungsmeldungen anzeigen (Strg+5)
+nav-performance = Leistung
+    .tooltip = GPU-Leistungskennzahlen überwachen
+nav-frame-pacing = Frame-Pacing
+    .tooltip = Present-Modi wechseln und Frame-Zeit und Latenz als Diagramm anzeigen
+nav-cpu-profiler = CPU-Profiler
+    .tooltip = Flame Graph der CPU-Zeit für Aufzeichnen, Übermitteln und Darstellen jedes Frames
+nav-puffin-viewer = Puffin-Viewer
+    .tooltip = Profiling-Spans aller Threads, aufgezeichnet mit dem Feature puffin
+nav-keybindings = Tastenkürzel
+    .tooltip = Die Tastenkürzel der Aktionen des Playgrounds neu belegen
+nav-settings = Einstellungen
+    .tooltip = Anwendungseinstellungen und Präferenzen (Strg+6)
+
+## Settings panel
+
+settings-heading = ⚙️ Einstellungen
+
+settings-language-heading = 🌐 Sprache
+settings-language-label = Sprache
+settings-language-coverage = { $percent } % übersetzt
+settings-language-help = Meldungen, die eine Übersetzung noch nicht enthält, werden auf Englisch angezeigt. Übersetzungen im Ordner locales des Asset-Verzeichnisses werden ebenfalls aufgeführt.
+settings-language-reload = 🔄 Übersetzungen neu laden
+settings-language-reloaded = ✓ { $count } Übersetzungsdateien geladen
+
+settings-theme-heading = Design
+settings-theme-prompt = Bevorzugtes Design der Oberfläche wählen:
+settings-theme-label = Design
+settings-theme-light = ☀️ Hell
+settings-theme-dark = 🌙 Dunkel
+settings-theme-custom = 🎨 Eigenes
+settings-theme-start-from = Ausgehend von:
+settings-theme-base = Grundstil
+settings-theme-panel-fill = Panel-Hintergrund
+settings-theme-window-fill = Fenster-Hintergrund
+settings-theme-accent = Akzent (Auswahl, Links)
+settings-theme-text = Text
+settings-theme-text-override = Überschreiben
+settings-theme-preview-clear = Löschfarbe der Vorschau
+settings-theme-backdrop = Vorschau-Hintergrund
+settings-theme-backdrop-preview = Die Löschfarbe der Vorschau über dem Hintergrund
+settings-theme-backdrop-solid = Einfarbig
+settings-theme-backdrop-checkerboard = Schachbrett
+settings-theme-backdrop-grid = Raster
+settings-theme-backdrop-stripes = Streifen
+settings-theme-help = Änderungen am Design werden sofort übernommen und automatisch gespeichert.
+
+settings-assets-heading = 📁 Asset-Verzeichnis
+settings-assets-help = Shader, Texturen und Modelle aus einem eigenen Verzeichnis laden. Fehlende Dateien werden aus den eingebauten Assets genommen.
+settings-assets-directory = Verzeichnis:
+settings-apply = Übernehmen
+settings-assets-use-builtin = Eingebaute verwenden
+settings-assets-using = ✓ Assets aus { $path } werden verwendet
+settings-assets-using-builtin = ✓ Eingebaute Assets werden verwendet
+settings-assets-not-a-directory = { $path } ist kein Verzeichnis
+settings-assets-effective = Verwendetes Verzeichnis:
+settings-env-variable = Kann auch über diese Umgebungsvariable gesetzt werden:
+settings-assets-recent = Letzte Änderungen (automatisch neu geladen):
+
+settings-cache-heading = 🗄️ Asset-Cache
+settings-cache-help = Verarbeitete Assets (z. B. importierte Modellgeometrie) werden nach Inhalts-Hash auf der Festplatte zwischengespeichert, damit Projekte schnell wieder geöffnet werden.
+settings-cache-open-failed = ✗ Asset-Cache konnte nicht geöffnet werden: { $error }
+settings-location = Ort:
+settings-cache-usage = { $used } von { $limit } ({ $entries } Einträge)
+settings-cache-entries = { $count } Einträge
+settings-cache-limit = Größenlimit (MiB):
+settings-cache-limit-set = ✓ Cache-Limit auf { $mib } MiB gesetzt
+settings-cache-limit-invalid = ✗ Das Größenlimit muss eine ganze Zahl in MiB sein
+settings-refresh = 🔄 Aktualisieren
+settings-clear-cache = 🗑 Cache leeren
+settings-cache-cleared = ✓ Asset-Cache geleert
+settings-cache-env-variable = Der Ort des Caches kann über diese Umgebungsvariable geändert werden:
+
+settings-pipeline-cache-heading = ⚡ Pipeline-Cache des Treibers
+settings-pipeline-cache-help = Vom Treiber kompilierte Shader werden beim Beenden gespeichert und beim nächsten Start geladen, sodass Pipelines ohne erneutes Kompilieren erstellt werden.
+settings-pipeline-cache-unavailable = Nicht verfügbar: Das aktuelle Backend oder Gerät hat keinen Pipeline-Cache.
+settings-pipeline-cache-loaded = Beim Start geladen: { $size }
+settings-pipeline-cache-size = Aktuelle Größe: { $size }
+settings-pipeline-cache-save = 💾 Jetzt speichern
+settings-pipeline-cache-saved = ✓ { $size } gespeichert
+settings-pipeline-cache-cleared = ✓ Pipeline-Cache geleert
+
+settings-backend-heading = 🔧 WebGPU-Backend
+settings-backend-prompt = Backend der WebGPU-Implementierung wählen:
+settings-backend-current = Aktuelles Backend:
+settings-backend-select = Backend wählen
+settings-backend-wgpu = 🦀 wgpu-rs (Rust-Implementierung)
+settings-backend-wgpu-tooltip = wgpu-rs ist eine Rust-Implementierung der WebGPU-API. Sie wird von Firefox und Servo verwendet und bietet eine vollständige, stabile WebGPU-Implementierung mit sehr guter Unterstützung für Vulkan, Metal, DirectX 12 und OpenGL.
+settings-backend-dawn = 🌅 Dawn Native (C++-Implementierung)
+settings-backend-dawn-tooltip = Dawn ist Googles C++-Implementierung der WebGPU-API. Sie wird von Chromium-basierten Browsern (Chrome, Edge, Opera) verwendet und bietet native WebGPU-Unterstützung mit Backends für Vulkan, Metal und DirectX 12.
+settings-backend-dawn-unavailable = 🌅 Dawn Native (nicht verfügbar)
+settings-backend-dawn-unavailable-tooltip = Dawn ist in diesem Build nicht enthalten. Mit --features dawn kompilieren, um Googles C++-Implementierung der WebGPU-API aus den Chromium-Browsern zu aktivieren.
+settings-backend-restart-warning = ⚠️ Achtung: Das Wechseln des Backends erfordert einen Neustart der Anwendung
+settings-backend-set-variable = Um die Änderung zu übernehmen, diese Umgebungsvariable setzen:
+settings-backend-restart = Danach die Anwendung neu starten.
+settings-backend-dawn-compiled = ✓ Dawn-Unterstützung ist einkompiliert
+settings-backend-dawn-missing = ℹ️ Dawn-Unterstützung nicht verfügbar (mit --features dawn kompilieren)
+settings-backend-available = 💡 Tipp: Verfügbare Backends:
+settings-backend-active = aktiv
+settings-backend-inactive = inaktiv
+
+## Keyboard shortcuts
+
+keybindings-heading = ⌨️ Tastenkürzel
+keybindings-help = Drücke ➕ neben einer Aktion und dann die Tasten des neuen Kürzels. Ein Klick auf ein Kürzel entfernt es. Änderungen werden sofort gespeichert.
+keybindings-column-action = Aktion
+keybindings-column-shortcuts = Kürzel
+keybindings-none = keine
+keybindings-add = ➕
+keybindings-add-tooltip = Kürzel hinzufügen
+keybindings-press-key = Taste drücken… (Esc bricht ab)
+keybindings-remove-tooltip = Klicken, um dieses Kürzel zu entfernen
+keybindings-reset = ↺ Zurücksetzen
+keybindings-reset-tooltip = Die Standardkürzel wiederherstellen
+keybindings-reset-all = ↺ Alle zurücksetzen
+keybindings-conflict = Auch belegt mit { $actions }
+keybindings-conflicts = ⚠ { $count } Kürzel sind mehreren Aktionen zugeordnet; ein Druck führt alle aus.
+keybindings-reserved = Auch verwendet für { $use }
+keybindings-reserved-text-editing = Textbearbeitung
+keybindings-reserved-zoom = UI-Zoom
+keybindings-saved = ✓ Gespeichert in { $path }
+keybindings-save-failed = ✗ Tastenkürzel konnten nicht gespeichert werden: { $error }
+keybindings-load-failed = ✗ Tastenkürzel konnten nicht geladen werden: { $error }
+
+keybindings-action-new-project = Neues Projekt
+keybindings-action-save-state = Zustand speichern
+keybindings-action-load-state = Zustand laden
+keybindings-action-undo = Rückgängig
+keybindings-action-redo = Wiederholen
+keybindings-action-compile-shader = Shader kompilieren
+keybindings-action-run-dispatch = Compute-Dispatch ausführen
+keybindings-action-screenshot = Screenshot speichern
+keybindings-action-capture-frame = Nächsten Frame in RenderDoc aufnehmen
+keybindings-action-toggle-preview = Vorschau starten/stoppen
+keybindings-action-command-palette = Befehlspalette öffnen
+keybindings-action-show-rendering = Rendering anzeigen
+keybindings-action-show-compute = Compute anzeigen
+keybindings-action-show-buffers = Buffer anzeigen
+keybindings-action-show-textures = Texturen anzeigen
+keybindings-action-show-console = Konsole anzeigen
+keybindings-action-show-settings = Einstellungen anzeigen
+
+## Command palette
+
+command-palette-title = Befehlspalette
+command-palette-button = 🔍 Befehle
+command-palette-button-tooltip = Beliebige Aktionen, Panels, Presets und Beispiele suchen und ausführen ({ $shortcut })
+command-palette-hint = Befehle, Panels, Presets und Beispiele suchen…
+command-palette-no-matches = Keine passenden Befehle
+command-palette-category-action = Aktion
+command-palette-category-panel = Panel
+command-palette-category-preset = Preset
+command-palette-category-example = Beispiel
+command-palette-open-panel = { $panel } öffnen
+command-palette-apply-preset = Preset anwenden: { $preset }
+command-palette-load-example = Beispiel laden: { $example }
+
+## Drag and drop
+
+drop-target-shader = Shader-Editor
+drop-target-texture = Texturen
+drop-target-model = Modell-Import
+drop-target-project = Projekt öffnen
+drop-overlay-target = { $file } ablegen, um es in { $target } zu öffnen
+drop-overlay-unsupported = { $file } kann hier nicht geöffnet werden
+drop-overlay-more = und { $count } weitere Dateien
+drop-opened = ✓ { $file } in { $target } geöffnet
+drop-unsupported = ✗ { $file } kann nicht geöffnet werden: Dateityp nicht unterstützt
+drop-failed = ✗ { $file } konnte nicht geöffnet werden: { $error }
+drop-needs-path = nur Dateien auf der Festplatte können als Modelle importiert werden
+
+## Clipboard
+
+paste-image-done = ✓ { $width }×{ $height }-Bild aus der Zwischenablage eingefügt
+paste-image-failed = ✗ Bild konnte nicht eingefügt werden: { $error }
+
+## Errors
+
+error-gpu-out-of-memory = GPU-Speicher erschöpft: { $source }
+error-gpu-validation = Validierungsfehler: { $source }
+error-gpu-internal = Interner Fehler: { $source }
@@ -0,0 +1,932 @@
+# English messages of the wgpu playground GUI.
+#
+# This catalog is the source of truth: every key used by the code is defined
+# here, and translations fall back to it for keys they don't have yet.
+# See README.md in this directory for the format and how to add a language.
+
+language-name = English
+
+## Tooltips
+
+tooltip-spec-link = WebGPU Spec
+tooltip-spec-link-hover = Click to open specification
+
+# Buffer usage flag tooltips
+tooltip-buffer-usage-vertex = Buffer can be used as a vertex buffer in draw commands. Contains per-vertex attributes like position, normal, UV coordinates, etc.
+tooltip-buffer-usage-index = Buffer can be used as an index buffer in indexed draw commands. Contains indices that reference vertices in vertex buffers.
+tooltip-buffer-usage-uniform = Buffer can be used as a uniform buffer. Contains read-only data accessible to shaders, typically for global parameters like transformation matrices.
+tooltip-buffer-usage-storage = Buffer can be used as a storage buffer. Allows shaders to read and write arbitrary data, useful for compute operations and large datasets.
+tooltip-buffer-usage-indirect = Buffer can be used for indirect draw commands. Contains draw parameters that can be dynamically determined on the GPU.
+tooltip-buffer-usage-copy-src = Buffer can be used as the source of a copy operation. Allows reading data from this buffer to copy to another buffer or texture.
+tooltip-buffer-usage-copy-dst = Buffer can be used as the destination of a copy operation. Allows writing data to this buffer from another buffer, texture, or the CPU.
+tooltip-buffer-usage-map-read = Buffer can be mapped for reading on the CPU. Allows reading GPU data back to the CPU, but cannot be used with MAP_WRITE.
+tooltip-buffer-usage-map-write = Buffer can be mapped for writing from the CPU. Allows uploading data to the GPU, but cannot be used with MAP_READ.
+tooltip-buffer-usage-query-resolve = Buffer can be used to resolve query results. Stores query data like timestamps or occlusion test results.
+
+# Texture usage flag tooltips
+tooltip-texture-usage-copy-src = Texture can be used as the source of a copy operation. Allows reading texture data to copy to another texture or buffer.
+tooltip-texture-usage-copy-dst = Texture can be used as the destination of a copy operation. Allows writing data to this texture from a buffer, another texture, or image uploads.
+tooltip-texture-usage-texture-binding = Texture can be bound to shaders for sampling. Allows shaders to read and sample texture data using samplers.
+tooltip-texture-usage-storage-binding = Texture can be bound as a storage texture. Allows shaders to read and write texture data directly without sampling, useful for compute operations.
+tooltip-texture-usage-render-attachment = Texture can be used as a render target. Allows rendering operations to write to this texture as a color, depth, or stencil attachment.
+
+# Primitive topology tooltips
+tooltip-primitive-topology-point-list = Each vertex represents a point. Useful for particle systems and point clouds.
+tooltip-primitive-topology-line-list = Every two vertices form an independent line segment. Useful for wireframe rendering and debug visualization.
+tooltip-primitive-topology-line-strip = Vertices form a connected sequence of lines. Each vertex connects to the next, creating a polyline.
+tooltip-primitive-topology-triangle-list = Every three vertices form an independent triangle. The most common topology for 3D rendering.
+tooltip-primitive-topology-triangle-strip = Vertices form a connected strip of triangles. More memory-efficient for certain mesh types like grids.
+
+# Cull mode tooltips
+tooltip-cull-mode-none = No face culling. Both front and back faces are rendered. Useful for 2D objects or double-sided geometry.
+tooltip-cull-mode-front = Cull front-facing triangles. Only back faces are rendered. Rarely used in practice.
+tooltip-cull-mode-back = Cull back-facing triangles. Only front faces are rendered. The most common culling mode for 3D objects.
+
+# Polygon mode tooltips
+tooltip-polygon-mode-fill = Triangles are filled. The only mode available in WebGPU.
+tooltip-polygon-mode-line = Only the edges of each triangle are drawn, giving a true wireframe of the mesh. Needs the POLYGON_MODE_LINE native feature.
+tooltip-polygon-mode-point = Only the vertices of each triangle are drawn, as points. Needs the POLYGON_MODE_POINT native feature.
+
+# Front face winding order tooltips
+tooltip-front-face-ccw = Counter-clockwise winding order defines front faces. Vertices ordered counter-clockwise (when viewed from the camera) are front-facing. This is the most common convention.
+tooltip-front-face-cw = Clockwise winding order defines front faces. Vertices ordered clockwise (when viewed from the camera) are front-facing.
+
+# Compare function tooltips (for depth/stencil testing)
+tooltip-compare-function-never = Comparison always fails. No fragments pass the test.
+tooltip-compare-function-less = Comparison passes if the new value is less than the stored value. Most common for depth testing.
+tooltip-compare-function-equal = Comparison passes if the new value equals the stored value.
+tooltip-compare-function-less-equal = Comparison passes if the new value is less than or equal to the stored value.
+tooltip-compare-function-greater = Comparison passes if the new value is greater than the stored value. Used for reverse-Z depth testing.
+tooltip-compare-function-not-equal = Comparison passes if the new value does not equal the stored value.
+tooltip-compare-function-greater-equal = Comparison passes if the new value is greater than or equal to the stored value.
+tooltip-compare-function-always = Comparison always passes. All fragments pass the test.
+
+# Blend factor tooltips
+tooltip-blend-factor-zero = Blend factor is 0. The source or destination color is multiplied by zero.
+tooltip-blend-factor-one = Blend factor is 1. The source or destination color is used as-is.
+tooltip-blend-factor-src = Blend factor is the source color.
+tooltip-blend-factor-one-minus-src = Blend factor is 1 minus the source color.
+tooltip-blend-factor-src-alpha = Blend factor is the source alpha.
+tooltip-blend-factor-one-minus-src-alpha = Blend factor is 1 minus the source alpha. Common for alpha blending.
+tooltip-blend-factor-dst = Blend factor is the destination color.
+tooltip-blend-factor-one-minus-dst = Blend factor is 1 minus the destination color.
+tooltip-blend-factor-dst-alpha = Blend factor is the destination alpha.
+tooltip-blend-factor-one-minus-dst-alpha = Blend factor is 1 minus the destination alpha.
+
+# Blend operation tooltips
+tooltip-blend-operation-add = Add source and destination colors. Most common blend operation.
+tooltip-blend-operation-subtract = Subtract destination from source color.
+tooltip-blend-operation-reverse-subtract = Subtract source from destination color.
+tooltip-blend-operation-min = Use the minimum of source and destination colors.
+tooltip-blend-operation-max = Use the maximum of source and destination colors.
+
+# Sampler address mode tooltips
+tooltip-address-mode-clamp-to-edge = Texture coordinates are clamped to [0, 1]. Coordinates outside this range use the edge color.
+tooltip-address-mode-repeat = Texture coordinates wrap around. Creates a tiling effect where the texture repeats.
+tooltip-address-mode-mirror-repeat = Texture coordinates wrap around with mirroring. The texture alternates between normal and mirrored on each repetition.
+tooltip-address-mode-clamp-to-border = Texture coordinates outside [0, 1] use the border color.
+
+# Sampler filter mode tooltips
+tooltip-filter-mode-nearest = Nearest neighbor filtering. Fast but can appear pixelated. Uses the color of the closest texel.
+tooltip-filter-mode-linear = Linear (bilinear) filtering. Smoother appearance. Interpolates between nearby texels.
+
+# Sampler property tooltips
+tooltip-sampler-lod-min-clamp = Minimum level of detail (LOD) to use when sampling. Lower values use higher resolution mipmap levels. Typically 0.0 to use the highest resolution.
+tooltip-sampler-lod-max-clamp = Maximum level of detail (LOD) to use when sampling. Higher values allow using lower resolution mipmap levels. Common values are 32.0 or the actual mip level count.
+tooltip-sampler-max-anisotropy = Maximum anisotropy level for anisotropic filtering. Higher values (up to 16) improve texture quality at oblique viewing angles but may reduce performance. A value of 1 disables anisotropic filtering.
+tooltip-sampler-border-color = The color to use when the address mode is ClampToBorder and texture coordinates fall outside [0, 1]. Choose from predefined colors: transparent black, opaque black, opaque white, or zero.
+
+# Stencil operation tooltips
+tooltip-stencil-operation-keep = Keep the current stencil value unchanged.
+tooltip-stencil-operation-zero = Set the stencil value to 0.
+tooltip-stencil-operation-replace = Replace the stencil value with the reference value.
+tooltip-stencil-operation-invert = Bitwise invert the stencil value.
+tooltip-stencil-operation-increment-clamp = Increment the stencil value, clamping to the maximum value.
+tooltip-stencil-operation-decrement-clamp = Decrement the stencil value, clamping to 0.
+tooltip-stencil-operation-increment-wrap = Increment the stencil value, wrapping to 0 on overflow.
+tooltip-stencil-operation-decrement-wrap = Decrement the stencil value, wrapping to the maximum value on underflow.
+
+# General property tooltips
+tooltip-property-buffer-size = Size of the buffer in bytes. Must be greater than 0 and aligned to the usage requirements.
+tooltip-property-buffer-mapped-at-creation = Whether the buffer should be mapped immediately after creation. Allows writing initial data without a separate map operation.
+tooltip-property-texture-width = Width of the texture in pixels. Must be greater than 0.
+tooltip-property-texture-height = Height of the texture in pixels. Must be greater than 0.
+tooltip-property-texture-depth = Depth or array layer count. For 2D textures, this is the number of array layers. For 3D textures, this is the depth.
+tooltip-property-texture-mip-levels = Number of mip levels. Mip levels are progressively smaller versions of the texture used for improved rendering quality at different distances.
+tooltip-property-texture-sample-count = Number of samples per pixel for multisampling. Must be 1 (no multisampling) or a power of 2 up to the device's maximum.
+tooltip-property-depth-write-enabled = Whether fragments can write to the depth buffer. Usually enabled for opaque objects and disabled for transparent ones.
+tooltip-property-alpha-to-coverage = Whether to use the alpha channel to determine coverage for multisampled rendering. Useful for foliage and transparent textures.
+tooltip-property-sample-count = Number of samples per pixel for multisampling anti-aliasing (MSAA). Higher values provide better quality but require more memory.
+
+# Shader visibility tooltips
+tooltip-shader-visibility-vertex = Resource is visible to vertex shaders. Vertex shaders process each vertex of geometry.
+tooltip-shader-visibility-fragment = Resource is visible to fragment shaders. Fragment shaders determine the color of each pixel.
+tooltip-shader-visibility-compute = Resource is visible to compute shaders. Compute shaders perform general-purpose GPU computation.
+
+# Compute dispatch tooltips
+tooltip-compute-workgroup-count-x = Number of workgroups dispatched in the X dimension. Each workgroup executes the compute shader with the workgroup size defined in the shader. Total invocations in X = workgroup_count_x * workgroup_size_x.
+tooltip-compute-workgroup-count-y = Number of workgroups dispatched in the Y dimension. Each workgroup executes the compute shader with the workgroup size defined in the shader. Total invocations in Y = workgroup_count_y * workgroup_size_y.
+tooltip-compute-workgroup-count-z = Number of workgroups dispatched in the Z dimension. Each workgroup executes the compute shader with the workgroup size defined in the shader. Total invocations in Z = workgroup_count_z * workgroup_size_z.
+tooltip-compute-entry-point = Name of the compute shader function to use as the entry point. This function must be marked with @compute attribute in WGSL.
+tooltip-compute-pipeline-layout = Defines the layout of bind groups for the compute pipeline. Can be auto-generated from the shader or manually specified to control bind group organization.
+
+# Render pass load/store operation tooltips
+tooltip-load-store-op-load-op-clear = Clear the attachment to a specified value at the start of the render pass. Discards previous contents and sets all pixels to the clear value.
+tooltip-load-store-op-load-op-load = Load the existing contents of the attachment at the start of the render pass. Preserves previous pixel values for modification or blending.
+tooltip-load-store-op-store-op-store = Store the results of the render pass to the attachment. Writes all rendered pixels to memory for later use.
+tooltip-load-store-op-store-op-discard = Discard the results of the render pass. The attachment contents become undefined after the pass. Useful for temporary rendering or when results aren't needed.
+tooltip-load-store-op-color-attachment = Color attachment configuration for the render pass. Specifies the texture to render into and how to handle its contents at the start and end of the pass.
+tooltip-load-store-op-depth-stencil-attachment = Depth-stencil attachment configuration for the render pass. Specifies the depth/stencil texture and operations for depth testing and stencil operations.
+
+# Draw command parameter tooltips
+tooltip-draw-vertex-count = Number of vertices to draw. For triangle lists, this is typically a multiple of 3. Each vertex is processed by the vertex shader.
+tooltip-draw-instance-count = Number of instances to draw. Instance count greater than 1 enables instanced rendering, where the same geometry is drawn multiple times with different instance data.
+tooltip-draw-first-vertex = Offset of the first vertex to draw from the vertex buffer. Allows drawing a subset of vertices from a buffer without creating separate buffers.
+tooltip-draw-first-instance = Offset of the first instance to draw. Affects the built-in instance_index value in the vertex shader, useful for accessing instance-specific data.
+
+## Navigation sidebar
+
+nav-heading = Navigation
+nav-quick = Quick navigation: { $modifier }+1-6
+nav-quick-shortcuts =
+    Keyboard shortcuts:
+    { $modifier }+1: Rendering
+    { $modifier }+2: Compute
+    { $modifier }+3: Buffers
+    { $modifier }+4: Textures
+    { $modifier }+5: Console
+    { $modifier }+6: Settings
+
+nav-setup = ⚙️ Setup & Configuration
+    .tooltip = Configure GPU adapter and device settings
+nav-adapter-selection = Adapter Selection
+    .tooltip = Choose and configure GPU adapters
+nav-device-config = Device Config
+    .tooltip = Enable/disable WebGPU features and adjust device limits
+nav-surface-config = Surface Config
+    .tooltip = Switch the window surface's format, present mode and alpha mode
+nav-device-info = Device Info
+    .tooltip = View comprehensive GPU adapter information
+
+nav-rendering = 🎨 Rendering & Graphics
+    .tooltip = Graphics rendering examples and pipeline configuration (Ctrl+1)
+nav-examples = Examples & Preview
+    .tooltip = View rendering examples and live preview
+nav-render-pipeline = Render Pipeline
+    .tooltip = Configure render pipeline and shaders
+nav-render-pass = Render Pass
+    .tooltip = Configure render pass settings
+nav-render-targets = Render Targets
+    .tooltip = Create off-screen targets, bind them in a render graph and inspect them
+nav-draw-commands = Draw Commands
+    .tooltip = Configure and execute draw commands
+nav-fragment-playground = Fragment Playground
+    .tooltip = Write ShaderToy-style fullscreen fragment shaders
+
+nav-compute = 🧮 Compute & ML
+    .tooltip = Compute shader operations and ML inferencing (Ctrl+2)
+nav-compute-panel = Compute Panel
+    .tooltip = Explore compute shader operations
+nav-compute-pipeline = Compute Pipeline
+    .tooltip = Configure compute pipeline and shaders
+nav-compute-dispatch = Compute Dispatch
+    .tooltip = Configure and dispatch compute operations
+nav-async-compute = Async Compute
+    .tooltip = Schedule chunked compute jobs and watch submission latency
+nav-workgroup-tuner = Workgroup Tuner
+    .tooltip = Benchmark a compute shader across workgroup sizes
+
+nav-resources = 📦 Resources
+    .tooltip = Manage GPU resources (buffers, textures, samplers)
+nav-buffers = Buffers
+    .tooltip = Create and configure GPU buffers (Ctrl+3)
+nav-textures = Textures
+    .tooltip = Load and configure textures from images (Ctrl+4)
+nav-samplers = Samplers
+    .tooltip = Configure texture sampling modes
+nav-models = 3D Models
+    .tooltip = Load and view 3D models
+nav-bind-groups = Bind Groups
+    .tooltip = Create bind groups for shader resources
+nav-bind-group-layouts = Bind Group Layouts
+    .tooltip = Define bind group layouts
+
+nav-tools = 🔧 Tools & Debugging
+    .tooltip = Debugging tools, console, and performance monitoring
+nav-tutorials = Tutorials
+    .tooltip = Interactive guided tutorials for learning WebGPU
+nav-learning-path = Learning Path
+    .tooltip = Visual learning path showing WebGPU concepts and progress
+nav-presets = Configuration Presets
+    .tooltip = Browse and load preset configurations for common rendering scenarios
+nav-history = History
+    .tooltip = Browse undo/redo history and jump to earlier states
+nav-resource-inspector = Resource Inspector
+    .tooltip = Inspect all GPU resources
+nav-buffer-inspector = Buffer Inspector
+    .tooltip = View and inspect buffer contents
+nav-texture-inspector = Texture Inspector
+    .tooltip = View and inspect texture data
+nav-pipeline-debugger = Pipeline Debugger
+    .tooltip = Debug render and compute pipelines
+nav-debug-counters = Debug Counters
+    .tooltip = Chart named atomic counters incremented by a shader
+nav-shader-variants = Shader Variants
+    .tooltip = Compile and compare ubershader permutations
+nav-shader-translation = Shader Translation
+    .tooltip = View the SPIR-V, HLSL, MSL and GLSL generated from the current shader
+nav-external-comparison = External Comparison
+    .tooltip = Compare output against images from other implementations
+nav-exercise-checks = Exercise Checks
+    .tooltip = Evaluate an exercise's assertions on the state and rendered output as you edit
+nav-command-recording = Command Recording
+    .tooltip = Record and replay GPU commands
+nav-api-coverage = API Coverage
+    .tooltip = Track and visualize WebGPU API usage
+nav-api-reference = API Reference
+    .tooltip = Browse WebGPU API documentation and examples
+nav-console = Console
+    .tooltip = View GPU errors and validation messages (Ctrl+5)
+nav-performance = Performance
+    .tooltip = Monitor GPU performance metrics
+nav-frame-pacing = Frame Pacing
+    .tooltip = Switch present modes and chart frame time and latency
+nav-cpu-profiler = CPU Profiler
+    .tooltip = Flame graph of the CPU time spent recording, submitting and presenting each frame
+nav-puffin-viewer = Puffin Viewer
+    .tooltip = Profiling spans from all threads, recorded with the puffin feature
+nav-keybindings = Keyboard Shortcuts
+    .tooltip = Remap the keyboard shortcuts of playground actions
+nav-settings = Settings
+    .tooltip = Application settings and preferences (Ctrl+6)
+
+## Settings panel
+
+settings-heading = ⚙️ Settings
+
+settings-language-heading = 🌐 Language
+settings-language-label = Language
+settings-language-coverage = { $percent }% translated
+settings-language-help = Messages a translation doesn't have yet are shown in English. Translations placed in the locales folder of the assets directory are listed too.
+settings-language-reload = 🔄 Reload Translations
+settings-language-reloaded = ✓ Loaded { $count } translation files
+
+settings-theme-heading = Theme
+settings-theme-prompt = Choose your preferred UI theme:
+settings-theme-label = Theme
+settings-theme-light = ☀️ Light
+settings-theme-dark = 🌙 Dark
+settings-theme-custom = 🎨 Custom
+settings-theme-start-from = Start from:
+settings-theme-base = Base style
+settings-theme-panel-fill = Panel background
+settings-theme-window-fill = Window background
+settings-theme-accent = Accent (selections, links)
+settings-theme-text = Text
+settings-theme-text-override = Override
+settings-theme-preview-clear = Preview clear color
+settings-theme-backdrop = Preview backdrop
+settings-theme-backdrop-preview = The preview clear color over the backdrop
+settings-theme-backdrop-solid = Solid
+settings-theme-backdrop-checkerboard = Checkerboard
+settings-theme-backdrop-grid = Grid
+settings-theme-backdrop-stripes = Stripes
+settings-theme-help = Theme changes are applied immediately and saved automatically.
+
+settings-assets-heading = 📁 Assets Directory
+settings-assets-help = Load shaders, textures and models from your own directory. Files missing there fall back to the built-in assets.
+settings-assets-directory = Directory:
+settings-apply = Apply
+settings-assets-use-builtin = Use Built-in
+settings-assets-using = ✓ Using assets from { $path }
+settings-assets-using-builtin = ✓ Using built-in assets
+settings-assets-not-a-directory = { $path } is not a directory
+settings-assets-effective = Effective directory:
+settings-env-variable = Can also be set with the environment variable
+settings-assets-recent = Recent changes (reloaded automatically):
+
+settings-cache-heading = 🗄️ Asset Cache
+settings-cache-help = Processed assets (e.g. imported model geometry) are cached on disk by content hash so reopening projects is fast.
+settings-cache-open-failed = ✗ Failed to open asset cache: { $error }
+settings-location = Location:
+settings-cache-usage = { $used } of { $limit } ({ $entries } entries)
+settings-cache-entries = { $count } entries
+settings-cache-limit = Size limit (MiB):
+settings-cache-limit-set = ✓ Cache limit set to { $mib } MiB
+settings-cache-limit-invalid = ✗ Size limit must be a whole number of MiB
+settings-refresh = 🔄 Refresh
+settings-clear-cache = 🗑 Clear Cache
+settings-cache-cleared = ✓ Asset cache cleared
+settings-cache-env-variable = The cache location can be changed with the environment variable
+
+settings-pipeline-cache-heading = ⚡ Driver Pipeline Cache
+settings-pipeline-cache-help = Shaders the driver compiled are saved on exit and loaded on the next launch, so pipelines are created without compiling them again.
+settings-pipeline-cache-unavailable = Not available: the current backend or device has no pipeline cache.
+settings-pipeline-cache-loaded = Loaded at startup: { $size }
+settings-pipeline-cache-size = Current size: { $size }
+settings-pipeline-cache-save = 💾 Save Now
+settings-pipeline-cache-saved = ✓ Saved { $size }
+settings-pipeline-cache-cleared = ✓ Pipeline cache cleared
+
+settings-backend-heading = 🔧 WebGPU Backend
+settings-backend-prompt = Select the WebGPU implementation backend:
+settings-backend-current = Current Backend:
+settings-backend-select = Select Backend
+settings-backend-wgpu = 🦀 wgpu-rs (Rust implementation)
+settings-backend-wgpu-tooltip = wgpu-rs is a Rust implementation of the WebGPU API. Used by Firefox and Servo, it provides a fully featured and stable WebGPU implementation with excellent support for Vulkan, Metal, DirectX 12, and OpenGL backends.
+settings-backend-dawn = 🌅 Dawn Native (C++ implementation)
+settings-backend-dawn-tooltip = Dawn is Google's C++ implementation of the WebGPU API. Used by Chromium-based browsers (Chrome, Edge, Opera), it provides native WebGPU support with backends for Vulkan, Metal, and DirectX 12.
+settings-backend-dawn-unavailable = 🌅 Dawn Native (Not Available)
+settings-backend-dawn-unavailable-tooltip = Dawn is not available in this build. Compile with --features dawn to enable Google's C++ implementation of the WebGPU API used by Chromium browsers.
+settings-backend-restart-warning = ⚠️ Warning: Backend switching requires application restart
+settings-backend-set-variable = To apply this change, set the environment variable:
+settings-backend-restart = Then restart the application.
+settings-backend-dawn-compiled = ✓ Dawn support is compiled in
+settings-backend-dawn-missing = ℹ️ Dawn support not available (compile with --features dawn)
+settings-backend-available = 💡 Tip: Available backends:
+settings-backend-active = active
+settings-backend-inactive = inactive
+
+## Keyboard shortcuts
+
+keybindings-heading = ⌨️ Keyboard Shortcuts
+keybindings-help = Press ➕ next to an action, then the keys of the new shortcut. Click a shortcut to remove it. Changes are saved right away.
+keybindings-column-action = Action
+keybindings-column-shortcuts = Shortcuts
+keybindings-none = none
+keybindings-add = ➕
+keybindings-add-tooltip = Add a shortcut
+keybindings-press-key = Press a key… (Esc cancels)
+keybindings-remove-tooltip = Click to remove this shortcut
+keybindings-reset = ↺ Reset
+keybindings-reset-tooltip = Go back to the default shortcuts
+keybindings-reset-all = ↺ Reset All
+keybindings-conflict = Also bound to { $actions }
+keybindings-conflicts = ⚠ { $count } shortcuts are bound to more than one action; pressing one runs all of them.
+keybindings-reserved = Also used for { $use }
+keybindings-reserved-text-editing = text editing
+keybindings-reserved-zoom = UI zoom
+keybindings-saved = ✓ Saved to { $path }
+keybindings-save-failed = ✗ Failed to save key bindings: { $error }
+keybindings-load-failed = ✗ Failed to load key bindings: { $error }
+
+keybindings-action-new-project = New project
+keybindings-action-save-state = Save state
+keybindings-action-load-state = Load state
+keybindings-action-undo = Undo
+keybindings-action-redo = Redo
+keybindings-action-compile-shader = Compile shader
+keybindings-action-run-dispatch = Run compute dispatch
+keybindings-action-screenshot = Save screenshot
+keybindings-action-capture-frame = Capture next frame in RenderDoc
+keybindings-action-toggle-preview = Start/stop preview
+keybindings-action-command-palette = Open command palette
+keybindings-action-show-rendering = Show Rendering
+keybindings-action-show-compute = Show Compute
+keybindings-action-show-buffers = Show Buffers
+keybindings-action-show-textures = Show Textures
+keybindings-action-show-console = Show Console
+keybindings-action-show-settings = Show Settings
+
+## Command palette
+
+command-palette-title = Command Palette
+command-palette-button = 🔍 Commands
+command-palette-button-tooltip = Search and run any action, panel, preset or example ({ $shortcut })
+command-palette-hint = Search commands, panels, presets and examples…
+command-palette-no-matches = No matching commands
+command-palette-category-action = Action
+command-palette-category-panel = Panel
+command-palette-category-preset = Preset
+command-palette-category-example = Example
+command-palette-open-panel = Open { $panel }
+command-palette-apply-preset = Apply preset: { $preset }
+command-palette-load-example = Load example: { $example }
+
+## Drag and drop
+
+drop-target-shader = Shader Editor
+drop-target-texture = Textures
+drop-target-model = Model Importer
+drop-target-project = Open Project
+drop-overlay-target = Drop { $file } to open it in { $target }
+drop-overlay-unsupported = { $file } can't be opened here
+drop-overlay-more = and { $count } more files
+drop-opened = ✓ Opened { $file } in { $target }
+drop-unsupported = ✗ Can't open { $file }: unsupported file type
+drop-failed = ✗ Failed to open { $file }: { $error }
+drop-needs-path = only files on disk can be imported as models
+
+## Clipboard
+
+paste-image-done = ✓ Pasted a { $width }×{ $height } image from the clipboard
+paste-image-failed = ✗ Failed to paste an image: { $error }
+
+## Errors
+
+error-gpu-out-of-memory = GPU out of memory: { $source }
+error-gpu-validation = Validation error: { $source }
+error-gpu-internal = Internal error: { $source }
+
+## Compute pipeline panel
+
+compute-pipeline-heading = ⚙️ Compute Pipeline Configuration
+compute-pipeline-intro = Configure and create compute pipelines for GPU compute operations.
+
+compute-pipeline-properties = Pipeline Properties
+compute-pipeline-label = Pipeline Label:
+compute-pipeline-label-hover = Optional label for debugging
+compute-pipeline-entry-point = Entry Point:
+
+compute-pipeline-shader-heading = Shader Module
+compute-pipeline-shader-intro = Configure the compute shader for this pipeline.
+compute-pipeline-shader-label = Shader Label:
+compute-pipeline-shader-label-hover = Optional label for the shader module
+compute-pipeline-shader-source = Shader Source (WGSL):
+compute-pipeline-shader-error = Shader compilation error: { $error }
+compute-pipeline-templates = Templates:
+compute-pipeline-template-simple = Simple Compute
+compute-pipeline-template-storage = Storage Buffer
+compute-pipeline-template-matrix = Matrix Multiply
+
+compute-pipeline-layout-heading = Pipeline Layout
+compute-pipeline-layout-intro = Configure how bind groups are organized in the pipeline.
+compute-pipeline-layout-auto = Use Auto-Generated Layout
+compute-pipeline-layout-manual-missing = ⚠️ Manual layout configuration not yet implemented
+compute-pipeline-layout-manual-fallback = For now, auto-generated layouts are used.
+
+compute-pipeline-validate = Validate Configuration
+compute-pipeline-create = ✨ Create Pipeline
+compute-pipeline-create-hover = Create the compute pipeline with current configuration
+compute-pipeline-create-needs-device = Pipeline creation requires GPU device access
+compute-pipeline-created = ✓ Compute pipeline created successfully: '{ $label }'
+compute-pipeline-unlabeled = unlabeled
+compute-pipeline-create-failed = Failed to create pipeline: { $error }
+compute-pipeline-error = ❌ Error: { $error }
+
+compute-pipeline-info-heading = ℹ️ Compute Pipeline Information
+compute-pipeline-info-parts = A compute pipeline consists of:
+compute-pipeline-info-shader = • Shader Module: Contains the compute shader code (WGSL)
+compute-pipeline-info-entry-point = • Entry Point: The function name to execute (e.g., 'main')
+compute-pipeline-info-layout = • Pipeline Layout: Defines bind group organization (auto-generated or manual)
+compute-pipeline-info-requirements = Compute shaders must have:
+compute-pipeline-info-compute = • @compute attribute on the entry point function
+compute-pipeline-info-workgroup-size = • @workgroup_size attribute specifying execution dimensions
+compute-pipeline-info-example = • Example: @compute @workgroup_size(64, 1, 1)
+
+## Sampler panel
+
+sampler-heading = 🎨 Sampler Configuration
+sampler-intro = Configure and create GPU samplers for texture sampling.
+
+sampler-properties = Sampler Properties
+sampler-label = Label:
+
+sampler-address-modes = Address Modes
+sampler-address-modes-intro = Control how texture coordinates outside [0, 1] are handled:
+sampler-address-u = U (horizontal):
+sampler-address-v = V (vertical):
+sampler-address-w = W (depth):
+
+sampler-filter-modes = Filter Modes
+sampler-filter-modes-intro = Control how textures are sampled and filtered:
+sampler-mag-filter = Magnification (zoom in):
+sampler-mag-filter-hover = Filter when pixel is smaller than texel
+sampler-min-filter = Minification (zoom out):
+sampler-min-filter-hover = Filter when pixel is larger than texel
+sampler-mipmap-filter = Mipmap:
+sampler-mipmap-filter-hover = Filter between mipmap levels
+
+sampler-lod-heading = LOD Clamping
+sampler-lod-intro = Limit the level of detail range:
+sampler-lod-min = Min LOD:
+sampler-lod-max = Max LOD:
+
+sampler-anisotropy-heading = Anisotropic Filtering
+sampler-anisotropy-intro = Improve texture quality at oblique angles (1 = disabled, 16 = maximum quality):
+sampler-anisotropy-level = Level
+
+sampler-compare-heading = Comparison Function
+sampler-compare-intro = Optional depth/stencil comparison for shadow mapping:
+sampler-compare-enable = Enable comparison
+sampler-compare-function = Function:
+
+sampler-border-heading = Border Color
+sampler-border-intro = Color used when address mode is ClampToBorder:
+sampler-border-enable = Enable border color
+sampler-border-color = Color:
+sampler-border-warning = ⚠ Border color should be enabled when using ClampToBorder
+sampler-border-transparent-black = Transparent Black
+sampler-border-opaque-black = Opaque Black
+sampler-border-opaque-white = Opaque White
+sampler-border-zero = Zero
+
+sampler-validate = 🔍 Validate
+sampler-create = ✨ Create Sampler
+sampler-reset = 🔄 Reset
+sampler-valid = ✓ Configuration is valid. In a full implementation, the sampler would be created here.
+sampler-created = ✓ Sampler created successfully: { $label }
+sampler-unnamed = <unnamed>
+sampler-create-failed = Failed to create sampler: { $error }
+sampler-error = ❌ { $error }
+
+sampler-preview-heading = 🔍 Mip Level Preview
+sampler-preview-intro = A textured plane receding into the distance, sampled with this configuration:
+sampler-preview-compare = Comparison samplers compare depth values instead of filtering colors; disable the comparison function to preview
+sampler-preview-error = ⚠ { $error }
+
+sampler-summary-heading = Configuration Summary
+sampler-summary-label = Label: { $label }
+sampler-summary-none = <none>
+sampler-summary-address-u = Address U:
+sampler-summary-address-v = Address V:
+sampler-summary-address-w = Address W:
+sampler-summary-mag-filter = Mag Filter:
+sampler-summary-min-filter = Min Filter:
+sampler-summary-mipmap-filter = Mipmap Filter:
+sampler-summary-lod-clamp = LOD Clamp:
+sampler-summary-anisotropy = Anisotropy:
+sampler-summary-compare = Compare:
+sampler-summary-compare-none = Compare: None
+sampler-summary-border = Border Color:
+sampler-summary-border-none = Border Color: None
+
+## Bind group panel
+
+bind-group-heading = 🔗 Bind Group Configuration
+bind-group-intro = Create bind group layouts and assign resources to binding slots.
+bind-group-mode-layout = 1️⃣ Create Layout
+bind-group-mode-resources = 2️⃣ Bind Resources
+bind-group-mode-visualization = 3️⃣ Visualization
+bind-group-error = ❌ { $error }
+
+bind-group-type-uniform-buffer = Uniform Buffer
+bind-group-type-storage-buffer-read-only = Storage Buffer (Read-Only)
+bind-group-type-storage-buffer-read-write = Storage Buffer (Read-Write)
+bind-group-type-texture = Texture
+bind-group-type-sampler = Sampler
+bind-group-type-storage-texture-write-only = Storage Texture (Write-Only)
+bind-group-type-storage-texture-read-only = Storage Texture (Read-Only)
+bind-group-type-storage-texture-read-write = Storage Texture (Read-Write)
+bind-group-access-write-only = Write-Only
+bind-group-access-read-only = Read-Only
+bind-group-access-read-write = Read-Write
+bind-group-access-tooltip = Write-only and read-only storage textures use Rgba8Unorm. Read-write access is only guaranteed for single-channel 32-bit formats, so it uses R32Float, and downlevel devices may not support it at all.
+
+bind-group-layout-empty = Bind group layout must have at least one entry
+bind-group-binding-invisible = Binding { $binding } must be visible in at least one shader stage
+bind-group-binding-vertex-storage = Binding { $binding }: writable storage textures are not allowed in the vertex stage
+bind-group-layout-valid = ✓ Layout configuration is valid
+bind-group-layout-created = ✓ Bind group layout created: { $label }
+bind-group-unnamed = <unnamed>
+bind-group-layout-create-failed = Failed to create layout: { $error }
+bind-group-needs-layout = Create a layout first before binding resources
+bind-group-binding-unassigned = Binding { $binding } has no resource assigned
+bind-group-bindings-valid = ✓ All bindings are assigned
+
+bind-group-layout-heading = Bind Group Layout
+bind-group-label = Label:
+bind-group-entries-heading = Binding Entries
+bind-group-entries-intro = Define the layout slots for resources:
+bind-group-entries-empty = No binding entries yet. Add one below.
+bind-group-column-binding = Binding
+bind-group-column-type = Type
+bind-group-column-vertex = Vertex
+bind-group-column-fragment = Fragment
+bind-group-column-compute = Compute
+bind-group-add-binding = Add new binding:
+bind-group-validate-layout = 🔍 Validate Layout
+bind-group-create-layout = ✨ Create Layout
+bind-group-create-layout-hover = Create the bind group layout on the GPU device
+bind-group-create-layout-needs-device = Layout creation requires GPU device access
+bind-group-reset = 🔄 Reset
+bind-group-layout-missing = ⚠ Create a bind group layout first in the 'Create Layout' tab
+
+bind-group-group-heading = Bind Group
+bind-group-summary-heading = Layout Summary
+bind-group-summary-layout = Layout: { $label }
+bind-group-summary-bindings = Bindings: { $count }
+bind-group-assignments-heading = Resource Assignments
+bind-group-assignments-intro = Assign resources to each binding slot:
+bind-group-assignment-binding = Binding { $binding }:
+bind-group-assigned = Assigned: { $name }
+bind-group-not-assigned = ⚠ Not assigned
+bind-group-validate-bindings = 🔍 Validate Bindings
+bind-group-create = ✨ Create Bind Group
+bind-group-valid = ✓ Configuration is valid. In a full implementation, the bind group would be created here.
+bind-group-available-buffers = Available Buffers:
+bind-group-buffer-details = { $size } bytes, { $usage }
+bind-group-available-textures = Available Textures:
+bind-group-available-samplers = Available Samplers:
+bind-group-sampler-details = Filter: { $filter }
+
+bind-group-diagram-heading = Bind Group Flow Diagram
+bind-group-diagram-intro = Visual representation of how resources flow through the rendering pipeline.
+bind-group-unknown-buffer = Unknown Buffer
+bind-group-unknown-texture = Unknown Texture
+bind-group-unknown-sampler = Unknown Sampler
+bind-group-diagram-help = How to read this diagram:
+bind-group-diagram-stages = • Pipeline Stages (left): Shader stages where bindings are accessible
+bind-group-diagram-bindings = • Bindings (center): Configured binding slots with their types
+bind-group-diagram-resources = • Resources (right): Actual GPU resources bound to each slot
+bind-group-diagram-arrows = • Arrows: Show the data flow from resources through bindings to pipeline stages
+bind-group-diagram-colors = • Colors: Different binding types are color-coded (see legend)
+
+## Buffer panel
+
+buffer-heading = 📐 Buffer Configuration
+buffer-intro = Configure and create GPU buffers with custom parameters.
+
+buffer-properties = Buffer Properties
+buffer-label = Label:
+buffer-label-tooltip = Optional label for debugging and identification
+buffer-size = Size (bytes):
+buffer-usage-heading = Usage Flags
+buffer-usage-intro = Select how the buffer will be used (multiple flags can be selected):
+buffer-usage-map-note = 💡 Note: MAP_READ and MAP_WRITE cannot be used together
+buffer-options-heading = Additional Options
+buffer-mapped-at-creation = Mapped at creation
+
+buffer-validate = 🔍 Validate
+buffer-create = ✨ Create Buffer
+buffer-reset = 🔄 Reset
+buffer-valid = ✓ Configuration is valid. In a full implementation, the buffer would be created here.
+buffer-created = ✓ Buffer created successfully: { $size } bytes
+buffer-create-failed = Failed to create buffer: { $error }
+buffer-error = ❌ { $error }
+
+buffer-data-heading = 📄 Buffer Data
+buffer-data-intro = Initialize the buffer from a CSV or raw binary file, or export its contents.
+buffer-data-file = File:
+buffer-data-file-hint = data.csv or data.bin
+buffer-data-file-hover = Files ending in .csv are parsed as CSV, anything else is raw binary
+buffer-data-import = 📥 Import
+buffer-data-choose-columns = Choose the column types to finish the import
+buffer-data-imported = ✓ Imported { $bytes } bytes from { $path }
+buffer-data-failed = ✗ { $error }
+buffer-data-export = 📤 Export
+buffer-data-export-hover = Export the created buffer (read back from the GPU) or the imported data
+buffer-data-exported = ✓ Exported { $bytes } bytes to { $path }
+buffer-data-clear = Clear Data
+buffer-data-columns = CSV columns:
+buffer-data-remove-column = Remove column
+buffer-data-initial = Initial data: { $bytes } bytes
+
+buffer-csv-title = CSV Column Types
+buffer-csv-rows = { $rows } rows. Each row is written as consecutive 32-bit values.
+buffer-csv-column = Column
+buffer-csv-type = Type
+buffer-csv-first-value = First value
+buffer-csv-stride = Row stride: { $stride } bytes, total: { $total } bytes
+buffer-csv-import = ✓ Import
+buffer-csv-cancel = Cancel
+buffer-csv-imported = ✓ Imported { $rows } rows ({ $bytes } bytes)
+
+buffer-summary-heading = Configuration Summary
+buffer-summary-label = Label: { $label }
+buffer-summary-none = <none>
+buffer-summary-size = Size: { $size } bytes
+buffer-summary-mapped = Mapped at creation: { $mapped }
+buffer-summary-usage = Usage flags:
+buffer-summary-usage-none = (none)
+
+buffer-preview-heading = 🎨 Live Preview
+buffer-preview-hide = Hide preview
+buffer-preview-vertex = Preview shows how this vertex buffer could render a simple triangle mesh:
+buffer-preview-uniform = Preview shows animated uniform buffer values affecting rendering:
+buffer-preview-needs-device = ⚠ Preview requires GPU device to be initialized
+buffer-preview-show = 🎨 Show Live Preview
+buffer-preview-wasm = ⚠ Preview is not available in WASM builds
+buffer-preview-wasm-note = 💡 Preview is not available in WASM builds
+buffer-preview-close = Close Preview
+
+## Texture panel
+
+texture-heading = 🖼️ Texture Configuration
+texture-intro = Configure and create GPU textures with custom parameters.
+
+texture-properties = Texture Properties
+texture-label = Label:
+texture-label-tooltip = Optional label for debugging and identification
+texture-width = Width:
+texture-height = Height:
+texture-depth = Depth/Array Layers:
+texture-mip-levels = Mip Levels:
+texture-sample-count = Sample Count:
+texture-dimension-heading = Texture Dimension
+texture-format-heading = Texture Format
+texture-format = Format
+texture-format-color = Color Formats
+texture-format-depth-stencil = Depth/Stencil Formats
+texture-format-compressed = Compressed Formats (BC)
+texture-usage-heading = Usage Flags
+texture-usage-intro = Select how the texture will be used (multiple flags can be selected):
+
+texture-width-invalid = Width must be a positive number
+texture-height-invalid = Height must be a positive number
+texture-depth-invalid = Depth/array layers must be a positive number
+texture-mip-levels-invalid = Mip levels must be a positive number
+texture-sample-count-invalid = Sample count must be 1, 2, 4, 8, 16, or 32
+texture-usage-empty = At least one usage flag must be selected
+texture-1d-height = 1D textures must have height = 1
+texture-1d-depth = 1D textures must have depth/array layers = 1
+texture-mip-levels-too-many = Mip levels ({ $levels }) exceeds maximum ({ $max }) for { $width }x{ $height } texture
+texture-multisampled-mips = Multisampled textures cannot have mip levels > 1
+texture-multisampled-dimension = Only 2D textures can be multisampled
+
+texture-file-heading = 📁 Load Texture from File
+texture-file-intro = Load image files (PNG, JPEG, KTX2, SVG) to create textures.
+texture-file-load = 📂 Load Image...
+texture-file-drop-window = Drag and drop an image file onto this window to load it.
+texture-file-drop-browser = Drag and drop an image file onto the browser window to load it.
+texture-file-paste = 📋 Paste Image
+texture-file-paste-hover = Load the image on the clipboard, e.g. a screenshot (Ctrl+V)
+texture-file-clear = 🗑️ Clear Loaded Image
+texture-file-loaded-size = 📐 Loaded image: { $width } x { $height } pixels
+texture-file-size-applied = Image dimensions have been applied to Width and Height fields.
+texture-file-tip = 💡 Tip: Drag and drop image files (PNG, JPEG, KTX2, SVG) onto the application window, or paste one from the clipboard with Ctrl+V, to load them.
+texture-file-tip-web = 💡 Tip: Drag and drop image files (PNG, JPEG, KTX2, SVG) onto the application window to load them.
+texture-file-loaded = ✓ Image loaded successfully: { $width }x{ $height } pixels
+texture-file-load-failed = Failed to load image: { $error }
+texture-file-saved-failed = Failed to load saved image: { $error }
+texture-file-decoding = Decoding image...
+texture-file-decode-waiting = Waiting to decode image...
+texture-file-cancel = Cancel
+texture-file-uploading = Uploading to the GPU
+texture-file-pasted = ✓ Image pasted from the clipboard: { $width }x{ $height } pixels
+texture-file-paste-failed = Failed to paste image: { $error }
+
+texture-dpi = 🔍 Resolution: { $dpi } DPI ({ $scale }x scale)
+texture-dpi-logical-size = Use logical size ({ $width } x { $height })
+texture-dpi-logical-size-hover = Set Width and Height to the image's size at { $dpi } DPI
+
+texture-svg-info = ✏ Vector image, { $width } x { $height } at its declared size. SVGs are resolution independent: rasterize again for a sharper or smaller texture.
+texture-svg-rasterize-at = Rasterize at:
+texture-svg-keep-aspect = Keep aspect ratio
+texture-svg-rasterize = 🔄 Re-rasterize
+texture-svg-rasterize-hover = Draw the SVG again at the size above
+
+texture-procedural-heading = 🎲 Procedural Generator
+texture-procedural-intro = Generate a pattern, shown in the preview, and bake it into a texture that bind groups can use.
+texture-bake = 🔥 Bake to Texture
+texture-bake-hover = Generate the pattern at Width x Height and upload it to a GPU texture
+texture-normal-map = 🗻 Normal Map from Image
+texture-normal-map-hover = Treat the loaded image's brightness as height and bake its normal map
+texture-baking = Baking texture...
+texture-baked-info = 🧱 Baked "{ $label }": { $width } x { $height } { $format }, available in the Bind Groups tab
+texture-bake-size = Textures are baked at sizes from 1 to { $max }
+texture-bake-failed = Failed to bake texture: { $error }
+texture-baked = ✓ Baked { $pattern } texture: { $width }x{ $height } pixels
+texture-normal-map-made = ✓ Normal map of the image: { $width }x{ $height } pixels
+texture-normal-map-failed = Failed to make a normal map: { $error }
+
+texture-preview-heading = 🎨 Texture Preview
+texture-preview-hide = Hide preview
+texture-preview-image = Preview shows the loaded image texture:
+texture-preview-procedural = Preview shows the procedural { $pattern } pattern:
+texture-preview-mip-view = 🔍 Mip level view
+texture-preview-mip-view-hover = Generate the configured mip levels and show which one is sampled across a receding plane
+texture-preview-single-mip = ⚠ The texture has a single mip level, so every distance samples level 0. Raise Mip Levels to build a chain
+texture-preview-needs-device = ⚠ Preview requires GPU device to be initialized
+texture-preview-show = 🎨 Show Texture Preview
+
+texture-validate = 🔍 Validate
+texture-config-valid = ✓ Configuration is valid
+texture-create = ✨ Create Texture
+texture-valid = ✓ Configuration is valid. In a full implementation, the texture would be created here.
+texture-reset = 🔄 Reset
+texture-error = ❌ { $error }
+
+texture-summary-heading = Configuration Summary
+texture-summary-label = Label: { $label }
+texture-summary-none = <none>
+texture-summary-dimension = Dimension:
+texture-summary-size = Size:
+texture-summary-format = Format:
+texture-summary-mip-levels = Mip Levels:
+texture-summary-sample-count = Sample Count:
+texture-summary-usage = Usage flags:
+texture-summary-usage-none = (none)
+
+## Render pipeline panel
+
+render-pipeline-heading = 🎨 Render Pipeline Configuration
+render-pipeline-intro = Configure comprehensive render pipeline settings with vertex, primitive, depth-stencil, multisample, and fragment states.
+render-pipeline-error = ❌ { $error }
+
+render-pipeline-presets-heading = 📋 Presets
+render-pipeline-presets-intro = Quick configuration presets:
+render-pipeline-preset-default = Default
+render-pipeline-preset-basic-triangle = Basic Triangle
+render-pipeline-preset-depth-tested = Depth Tested
+render-pipeline-preset-alpha-blended = Alpha Blended
+render-pipeline-preset-wireframe = Wireframe
+render-pipeline-preset-msaa = 4x MSAA
+render-pipeline-preset-applied = ✓ Preset '{ $preset }' applied
+
+render-pipeline-properties = Pipeline Properties
+render-pipeline-label = Label:
+render-pipeline-vertex-heading = 🔺 Vertex State
+render-pipeline-vertex-intro = Configure vertex shader entry point:
+render-pipeline-vertex-entry-point = Vertex Entry Point:
+render-pipeline-primitive-heading = 🔷 Primitive State
+render-pipeline-primitive-intro = Configure primitive topology and culling:
+render-pipeline-topology = Topology:
+render-pipeline-topology-hover = How vertices are assembled into primitives
+render-pipeline-cull-mode = Cull Mode:
+render-pipeline-cull-mode-hover = Which faces to cull (not render)
+render-pipeline-front-face = Front Face:
+render-pipeline-front-face-hover = Winding order that determines front-facing
+render-pipeline-polygon-mode = Polygon Mode:
+render-pipeline-polygon-mode-hover = Whether triangles are filled or drawn as edges or points
+render-pipeline-polygon-mode-feature = Requires the { $feature } device feature
+
+render-pipeline-depth-stencil-heading = 📏 Depth-Stencil State
+render-pipeline-depth-stencil-enable = Enable Depth-Stencil Testing
+render-pipeline-depth-format = Depth Format:
+render-pipeline-depth-write = Depth Write:
+render-pipeline-enabled = Enabled
+render-pipeline-depth-compare = Depth Compare:
+render-pipeline-depth-compare-hover = Comparison function for depth test
+render-pipeline-stencil-read-mask = Stencil Read Mask:
+render-pipeline-stencil-write-mask = Stencil Write Mask:
+render-pipeline-stencil-front = Stencil Front Face
+render-pipeline-stencil-back = Stencil Back Face
+render-pipeline-stencil-compare = Compare:
+render-pipeline-stencil-fail = Fail Operation:
+render-pipeline-stencil-depth-fail = Depth Fail Operation:
+render-pipeline-stencil-pass = Pass Operation:
+
+render-pipeline-multisample-heading = 🔬 Multisample State
+render-pipeline-multisample-intro = Configure multisampling anti-aliasing:
+render-pipeline-sample-count = Sample Count:
+render-pipeline-sample-count-hover = Number of samples per pixel (1, 2, 4, or 8)
+render-pipeline-alpha-to-coverage = Alpha to Coverage:
+render-pipeline-alpha-to-coverage-hover = Enable alpha to coverage for transparency
+
+render-pipeline-fragment-heading = 🎨 Fragment State
+render-pipeline-fragment-intro = Configure fragment shader and color output:
+render-pipeline-fragment-entry-point = Fragment Entry Point:
+render-pipeline-target-format = Target Format:
+render-pipeline-blending = Blending:
+render-pipeline-blending-enable = Enable Blending
+render-pipeline-color-blend = Color Blend
+render-pipeline-alpha-blend = Alpha Blend
+render-pipeline-blend-src = Source Factor:
+render-pipeline-blend-dst = Destination Factor:
+render-pipeline-blend-operation = Operation:
+render-pipeline-blend-visualizer = Blend Visualizer
+render-pipeline-write-mask = Color Write Mask:
+render-pipeline-write-red = Red
+render-pipeline-write-green = Green
+render-pipeline-write-blue = Blue
+render-pipeline-write-alpha = Alpha
+
+render-pipeline-update = 📝 Update Configuration
+render-pipeline-updated = ✓ Configuration updated
+render-pipeline-reset = 🔄 Reset to Default
+
+render-pipeline-preview-heading = 🎬 Pipeline Preview
+render-pipeline-preview-hide = Hide preview
+render-pipeline-preview-intro = Preview shows how this pipeline configuration affects rendering of a 3D scene:
+render-pipeline-preview-topology = • Topology: Triangle/Line primitives
+render-pipeline-preview-culling = • Culling: Front/back face visibility
+render-pipeline-preview-depth = • Depth: Z-buffer testing effect
+render-pipeline-preview-blending = • Blending: Color composition
+render-pipeline-preview-ab = Left: baseline (A), right: current settings (B).
+render-pipeline-baseline = A (baseline)
+render-pipeline-current = B (current)
+render-pipeline-preview-scene = 🌳 Scene
+render-pipeline-preview-needs-device = ⚠ Preview requires GPU device to be initialized
+render-pipeline-test = 🎬 Test Pipeline
+render-pipeline-test-hover = Show live preview of pipeline configuration
+render-pipeline-stencil-demo = 🧱 Stencil Step-through
+render-pipeline-stencil-demo-needs-device = ⚠ The stencil demo requires GPU device to be initialized
+
+render-pipeline-cache = 📦 Pipeline Cache
+render-pipeline-cache-intro = The previews look up their pipelines by a hash of the full descriptor and shader source, so returning to an earlier configuration reuses its compiled pipeline. The least recently used pipeline is evicted once the cache is full.
+render-pipeline-cache-pipelines = Pipelines:
+render-pipeline-cache-hits = Hits:
+render-pipeline-cache-misses = Misses:
+render-pipeline-cache-hit-rate = Hit rate:
+render-pipeline-cache-evictions = Evictions:
+render-pipeline-cache-invalidations = Invalidations:
+render-pipeline-cache-invalidations-hover = Pipelines dropped because a shader they were built from changed
+render-pipeline-cache-capacity = Capacity:
+render-pipeline-cache-clear = 🗑 Clear
+render-pipeline-cache-clear-hover = Drop every cached pipeline
+render-pipeline-cache-reset = ↺ Reset Counters
+
+render-pipeline-ab-heading = 🔀 A/B Comparison
+render-pipeline-ab-intro = Snapshot the current configuration as baseline (A), change settings (B) and compare them field by field.
+render-pipeline-ab-resnapshot = 📌 Re-snapshot Baseline
+render-pipeline-ab-snapshot = 📌 Snapshot Baseline
+render-pipeline-ab-snapshot-hover = Store the current configuration as configuration A
+render-pipeline-ab-snapshot-taken = ✓ Baseline snapshot taken
+render-pipeline-ab-clear = 🗑 Clear Baseline
+render-pipeline-ab-none = No baseline yet.
+render-pipeline-ab-identical = ✓ Current configuration matches the baseline
+render-pipeline-ab-changed = { $count } field(s) changed in: { $sections }
+render-pipeline-ab-only-changed = Only show changed fields
+render-pipeline-ab-section = Section
+render-pipeline-ab-field = Field
@@ -0,0 +1,189 @@
+# Spanish messages of the wgpu playground GUI.
+#
+# Partial translation: the navigation and the settings panel are translated,
+# the WebGPU tooltips and the editor panels are still shown in English.
+
+language-name = Español
+
+## Navigation sidebar
+
+nav-heading = Navegación
+nav-quick = Navegación rápida: { $modifier }+1-6
+nav-quick-shortcuts =
+    Atajos de teclado:
+    { $modifier }+1: Renderizado
+    { $modifier }+2: Cómputo
+    { $modifier }+3: Búferes
+    { $modifier }+4: Texturas
+    { $modifier }+5: Consola
+    { $modifier }+6: Ajustes
+
+nav-setup = ⚙️ Preparación y configuración
+    .tooltip = Configurar el adaptador de GPU y el dispositivo
+nav-adapter-selection = Selección de adaptador
+    .tooltip = Elegir y configurar adaptadores de GPU
+nav-device-config = Configuración del dispositivo
+    .tooltip = Activar o desactivar funciones de WebGPU y ajustar los límites del dispositivo
+nav-surface-config = Configuración de la superficie
+    .tooltip = Cambiar el formato, el modo de presentación y el modo alfa de la superficie de la ventana
+nav-device-info = Información del dispositivo
+    .tooltip = Ver información detallada del adaptador de GPU
+
+nav-rendering = 🎨 Renderizado y gráficos
+    .tooltip = Ejemplos de renderizado y configuración de pipelines (Ctrl+1)
+nav-examples = Ejemplos y vista previa
+    .tooltip = Ver ejemplos de renderizado y la vista previa en vivo
+nav-render-pipeline = Pipeline de renderizado
+    .tooltip = Configurar el pipeline de renderizado y los shaders
+nav-render-pass = Pase de renderizado
+    .tooltip = Configurar el pase de renderizado
+nav-render-targets = Destinos de renderizado
+    .tooltip = Crear destinos fuera de pantalla, conectarlos en un grafo de renderizado e inspeccionarlos
+nav-draw-commands = Comandos de dibujo
+    .tooltip = Configurar y ejecutar comandos de dibujo
+nav-fragment-playground = Zona de pruebas de fragmentos
+    .tooltip = Escribir shaders de fragmentos a pantalla completa al estilo de ShaderToy
+
+nav-compute = 🧮 Cómputo y ML
+    .tooltip = Operaciones con compute shaders e inferencia de ML (Ctrl+2)
+nav-compute-panel = Panel de cómputo
+    .tooltip = Explorar operaciones con compute shaders
+nav-compute-pipeline = Pipeline de cómputo
+    .tooltip = Configurar el pipeline de cómputo y los shaders
+nav-compute-dispatch = Despacho de cómputo
+    .tooltip = Configurar y despachar operaciones de cómputo
+nav-async-compute = Cómputo asíncrono
+    .tooltip = Planificar trabajos de cómputo por partes y observar la latencia de envío
+nav-workgroup-tuner = Ajuste de workgroups
+    .tooltip = Medir un compute shader con distintos tamaños de workgroup
+
+nav-resources = 📦 Recursos
+    .tooltip = Gestionar recursos de GPU (búferes, texturas, samplers)
+nav-buffers = Búferes
+    .tooltip = Crear y configurar búferes de GPU (Ctrl+3)
+nav-textures = Texturas
+    .tooltip = Cargar y configurar texturas a partir de imágenes (Ctrl+4)
+nav-samplers = Samplers
+    .tooltip = Configurar los modos de muestreo de texturas
+nav-models = Modelos 3D
+    .tooltip = Cargar y ver modelos 3D
+nav-bind-groups = Bind groups
+    .tooltip = Crear bind groups para los recursos de los shaders
+nav-bind-group-layouts = Layouts de bind groups
+    .tooltip = Definir layouts de bind groups
+
+nav-tools = 🔧 Herramientas y depuración
+    .tooltip = Herramientas de depuración, consola y monitorización del rendimiento
+nav-tutorials = Tutoriales
+    .tooltip = Tutoriales guiados e interactivos para aprender WebGPU
+nav-learning-path = Ruta de aprendizaje
+    .tooltip = Ruta de aprendizaje visual con los conceptos de WebGPU y tu progreso
+nav-presets = Configuraciones predefinidas
+    .tooltip = Explorar y cargar configuraciones para escenarios de renderizado habituales
+nav-history = Historial
+    .tooltip = Explorar el historial de deshacer/rehacer y volver a estados anteriores
+nav-resource-inspector = Inspector de recursos
+    .tooltip = Inspeccionar todos los recursos de GPU
+nav-buffer-inspector = Inspector de búferes
+    .tooltip = Ver e inspeccionar el contenido de los búferes
+nav-texture-inspector = Inspector de texturas
+    .tooltip = Ver e inspeccionar los datos de las texturas
+nav-pipeline-debugger = Depurador de pipelines
+    .tooltip = Depurar pipelines de renderizado y de cómputo
+nav-debug-counters = Contadores de depuración
+    .tooltip = Graficar contadores atómicos con nombre que incrementa un shader
+nav-shader-variants = Variantes de shaders
+    .tooltip = Compilar y comparar permutaciones de un ubershader
+nav-shader-translation = Traducción de shaders
+    .tooltip = Ver el SPIR-V, HLSL, MSL y GLSL generados a partir del shader actual
+nav-external-comparison = Comparación externa
+    .tooltip = Comparar la salida con imágenes de otras implementaciones
+nav-exercise-checks = Comprobación de ejercicios
+    .tooltip = Evaluar las comprobaciones de un ejercicio sobre el estado y la salida renderizada mientras editas
+nav-command-recording = Grabación de comandos
+    .tooltip = Grabar y reproducir comandos de GPU
+nav-api-coverage = Cobertura de la API
+    .tooltip = Seguir y visualizar el uso de la API de WebGPU
+nav-api-reference = Referencia de la API
+    .tooltip = Consultar la documentación y los ejemplos de la API de WebGPU
+nav-console = Consola
+    .tooltip = Ver errores de GPU y mensajes de validación (Ctrl+5)
+nav-performance = Rendimiento
+    .tooltip = Monitorizar las métricas de rendimiento de la GPU
+nav-frame-pacing = Ritmo de fotogramas
+    .tooltip = Cambiar el modo de presentación y graficar el tiempo de fotograma y la latencia
+nav-cpu-profiler = Perfilador de CPU
+    .tooltip = Gráfico de llamas del tiempo de CPU empleado en grabar, enviar y presentar cada fotograma
+nav-puffin-viewer = Visor de Puffin
+    .tooltip = Intervalos de perfilado de todos los hilos, grabados con la función puffin
+nav-settings = Ajustes
+    .tooltip = Ajustes y preferencias de la aplicación (Ctrl+6)
+
+## Settings panel
+
+settings-heading = ⚙️ Ajustes
+
+settings-language-heading = 🌐 Idioma
+settings-language-label = Idioma
+settings-language-coverage = { $percent } % traducido
+settings-language-help = Los mensajes que una traducción aún no tiene se muestran en inglés. También se listan las traducciones de la carpeta locales del directorio de recursos.
+settings-language-reload = 🔄 Recargar traducciones
+settings-language-reloaded = ✓ { $count } archivos de traducción cargados
+
+settings-theme-heading = Tema
+settings-theme-prompt = Elige el tema de la interfaz:
+settings-theme-label = Tema
+settings-theme-light = ☀️ Claro
+settings-theme-dark = 🌙 Oscuro
+settings-theme-help = Los cambios de tema se aplican al instante y se guardan automáticamente.
+
+settings-assets-heading = 📁 Directorio de recursos
+settings-assets-help = Carga shaders, texturas y modelos desde tu propio directorio. Los archivos que falten ahí se toman de los recursos incluidos.
+settings-assets-directory = Directorio:
+settings-apply = Aplicar
+settings-assets-use-builtin = Usar los incluidos
+settings-assets-using = ✓ Usando recursos de { $path }
+settings-assets-using-builtin = ✓ Usando los recursos incluidos
+settings-assets-not-a-directory = { $path } no es un directorio
+settings-assets-effective = Directorio en uso:
+settings-env-variable = También se puede establecer con la variable de entorno
+settings-assets-recent = Cambios recientes (recargados automáticamente):
+
+settings-cache-heading = 🗄️ Caché de recursos
+settings-cache-help = Los recursos procesados (p. ej. la geometría de modelos importados) se guardan en disco por hash de contenido para que reabrir proyectos sea rápido.
+settings-cache-open-failed = ✗ No se pudo abrir la caché de recursos: { $error }
+settings-location = Ubicación:
+settings-cache-usage = { $used } de { $limit } ({ $entries } entradas)
+settings-cache-entries = { $count } entradas
+settings-cache-limit = Límite de tamaño (MiB):
+settings-cache-limit-set = ✓ Límite de la caché fijado en { $mib } MiB
+settings-cache-limit-invalid = ✗ El límite de tamaño debe ser un número entero de MiB
+settings-refresh = 🔄 Actualizar
+settings-clear-cache = 🗑 Vaciar caché
+settings-cache-cleared = ✓ Caché de recursos vaciada
+settings-cache-env-variable = La ubicación de la caché se puede cambiar con la variable de entorno
+
+settings-pipeline-cache-heading = ⚡ Caché de pipelines del controlador
+settings-pipeline-cache-help = Los shaders que compiló el controlador se guardan al salir y se cargan en el siguiente inicio, así los pipelines se crean sin volver a compilarlos.
+settings-pipeline-cache-unavailable = No disponible: el backend o el dispositivo actual no tiene caché de pipelines.
+settings-pipeline-cache-loaded = Cargado al inicio: { $size }
+settings-pipeline-cache-size = Tamaño actual: { $size }
+settings-pipeline-cache-save = 💾 Guardar ahora
+settings-pipeline-cache-saved = ✓ Guardado { $size }
+settings-pipeline-cache-cleared = ✓ Caché de pipelines vaciada
+
+settings-backend-heading = 🔧 Backend de WebGPU
+settings-backend-prompt = Selecciona el backend de la implementación de WebGPU:
+settings-backend-current = Backend actual:
+settings-backend-select = Seleccionar backend
+settings-backend-wgpu = 🦀 wgpu-rs (implementación en Rust)
+settings-backend-dawn = 🌅 Dawn Native (implementación en C++)
+settings-backend-dawn-unavailable = 🌅 Dawn Native (no disponible)
+settings-backend-restart-warning = ⚠️ Atención: cambiar de backend requiere reiniciar la aplicación
+settings-backend-set-variable = Para aplicar el cambio, establece la variable de entorno:
+settings-backend-restart = Después reinicia la aplicación.
+settings-backend-dawn-compiled = ✓ La compatibilidad con Dawn está compilada
+settings-backend-dawn-missing = ℹ️ Compatibilidad con Dawn no disponible (compila con --features dawn)
+settings-backend-available = 💡 Consejo: backends disponibles:
+settings-backend-active = activo
+settings-backend-inactive = inactivo
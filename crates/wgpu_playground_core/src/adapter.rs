@@ -219,11 +219,55 @@ pub fn backend_input_options() -> Vec<&'static str> {
     vec!["vulkan", "metal", "dx12", "gl", "webgpu", "primary", "all"]
 }
 
+/// Options for creating a wgpu Instance
+#[derive(Debug, Clone)]
+pub struct InstanceOptions {
+    /// Backend(s) the Instance may use
+    pub backends: Backends,
+    /// When resource creation fails or the device is lost for memory
+    /// pressure; wgpu's defaults leave it to the driver
+    pub memory_budget_thresholds: wgpu::MemoryBudgetThresholds,
+}
+
+impl Default for InstanceOptions {
+    fn default() -> Self {
+        Self::new(Backends::all())
+    }
+}
+
+impl InstanceOptions {
+    /// Options for an Instance with the given backends and no budget thresholds
+    pub fn new(backends: Backends) -> Self {
+        Self {
+            backends,
+            memory_budget_thresholds: wgpu::MemoryBudgetThresholds::default(),
+        }
+    }
+
+    /// Set the memory budget thresholds
+    ///
+    /// The GUI uses [`BUDGET_THRESHOLDS`](crate::memory_budget::BUDGET_THRESHOLDS)
+    /// so allocations near the native budget fail instead of paging.
+    pub fn with_memory_budget_thresholds(
+        mut self,
+        thresholds: wgpu::MemoryBudgetThresholds,
+    ) -> Self {
+        self.memory_budget_thresholds = thresholds;
+        self
+    }
+}
+
 /// Create a wgpu Instance with the specified backends
 pub fn create_instance(backends: Backends) -> Instance {
-    log::debug!("Creating wgpu Instance with backends: {:?}", backends);
+    create_instance_from(&InstanceOptions::new(backends))
+}
+
+/// Create a wgpu Instance from [`InstanceOptions`]
+pub fn create_instance_from(options: &InstanceOptions) -> Instance {
+    log::debug!("Creating wgpu Instance with options: {:?}", options);
     let instance = Instance::new(wgpu::InstanceDescriptor {
-        backends,
+        backends: options.backends,
+        memory_budget_thresholds: options.memory_budget_thresholds,
         ..wgpu::InstanceDescriptor::new_without_display_handle()
     });
     log::trace!("Instance created successfully");
@@ -423,6 +467,19 @@ mod tests {
         drop(instance);
     }
 
+    #[test]
+    fn test_instance_options_default_to_no_thresholds() {
+        let options = InstanceOptions::new(Backends::VULKAN);
+        assert_eq!(options.memory_budget_thresholds.for_resource_creation, None);
+        let options =
+            options.with_memory_budget_thresholds(crate::memory_budget::BUDGET_THRESHOLDS);
+        assert_eq!(
+            options.memory_budget_thresholds.for_resource_creation,
+            Some(95)
+        );
+        drop(create_instance_from(&options));
+    }
+
     #[test]
     fn test_backend_to_str() {
         assert_eq!(backend_to_str(&wgpu::Backend::Vulkan), "Vulkan");
@@ -0,0 +1,513 @@
+//! Content-addressed on-disk cache for processed assets
+//!
+//! Deriving GPU-ready data from source assets (parsing and triangulating
+//! meshes, decoding and compressing textures, generating mips, prefiltering
+//! environment maps) can take much longer than reading the result back from
+//! disk. [`AssetCache`] stores such results under a [`CacheKey`] computed
+//! from the source bytes plus every parameter that influences the output, so
+//! a changed source file or option simply produces a new key and stale
+//! entries are never returned.
+//!
+//! Entries are plain files below `<root>/<kind>/`; the total size is kept
+//! under a configurable limit by evicting the least recently used entries.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Environment variable overriding the cache directory
+pub const CACHE_DIR_ENV: &str = "WGPU_PLAYGROUND_CACHE";
+
+/// Cache directory used when [`CACHE_DIR_ENV`] is not set
+pub const DEFAULT_CACHE_DIR: &str = ".wgpu_playground_cache";
+
+/// Default size limit of the cache (512 MiB)
+pub const DEFAULT_MAX_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Bumped whenever the key derivation changes, invalidating all entries
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Settings file stored in the cache root
+const CONFIG_FILE: &str = "cache_config.json";
+
+/// Extension of cache entry files
+const ENTRY_EXTENSION: &str = "bin";
+
+/// 128-bit FNV-1a hasher, stable across platforms and Rust versions
+#[derive(Debug, Clone)]
+struct ContentHasher(u128);
+
+impl ContentHasher {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u128;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Write a length-prefixed field so that adjacent fields cannot alias
+    fn write_field(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn finish(&self) -> String {
+        format!("{:032x}", self.0)
+    }
+}
+
+/// Identifies a cached asset by kind, source content and parameters
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    kind: String,
+    hash: String,
+}
+
+impl CacheKey {
+    /// Start building a key for an asset kind such as `"mesh"` or `"mips"`
+    ///
+    /// # Examples
+    /// ```
+    /// use wgpu_playground_core::asset_cache::CacheKey;
+    ///
+    /// let key = CacheKey::builder("mips")
+    ///     .content(b"...png bytes...")
+    ///     .param("filter", "box")
+    ///     .param("levels", 8)
+    ///     .build();
+    /// assert_eq!(key.kind(), "mips");
+    /// ```
+    pub fn builder(kind: &str) -> CacheKeyBuilder {
+        let mut hasher = ContentHasher::new();
+        hasher.write(&CACHE_FORMAT_VERSION.to_le_bytes());
+        hasher.write_field(kind.as_bytes());
+        CacheKeyBuilder {
+            kind: sanitize_kind(kind),
+            hasher,
+        }
+    }
+
+    /// Asset kind, also the subdirectory of the entry
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Hex digest of kind, content and parameters
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.kind, self.hash)
+    }
+}
+
+/// Builder for [`CacheKey`]
+#[derive(Debug, Clone)]
+pub struct CacheKeyBuilder {
+    kind: String,
+    hasher: ContentHasher,
+}
+
+impl CacheKeyBuilder {
+    /// Add source content, e.g. the bytes of a model or image file
+    pub fn content(mut self, bytes: &[u8]) -> Self {
+        self.hasher.write(b"content");
+        self.hasher.write_field(bytes);
+        self
+    }
+
+    /// Add a named parameter that affects the processed output
+    pub fn param(mut self, name: &str, value: impl fmt::Display) -> Self {
+        self.hasher.write(b"param");
+        self.hasher.write_field(name.as_bytes());
+        self.hasher.write_field(value.to_string().as_bytes());
+        self
+    }
+
+    /// Finish the key
+    pub fn build(self) -> CacheKey {
+        CacheKey {
+            kind: self.kind,
+            hash: self.hasher.finish(),
+        }
+    }
+}
+
+/// Restrict kinds to characters that are safe as a directory name
+fn sanitize_kind(kind: &str) -> String {
+    let kind: String = kind
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if kind.is_empty() {
+        "misc".to_string()
+    } else {
+        kind
+    }
+}
+
+/// Persisted cache settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheConfig {
+    max_bytes: u64,
+}
+
+/// A single file in the cache
+#[derive(Debug, Clone)]
+pub struct CacheEntryInfo {
+    /// Asset kind the entry belongs to
+    pub kind: String,
+    /// Location of the entry file
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    /// Last time the entry was written or read
+    pub last_used: SystemTime,
+}
+
+/// Summary of the cache contents
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheStats {
+    /// Number of entries
+    pub entries: usize,
+    /// Total size of all entries in bytes
+    pub total_bytes: u64,
+    /// Entry count and size per asset kind
+    pub per_kind: BTreeMap<String, (usize, u64)>,
+}
+
+/// Content-addressed cache of processed assets on disk
+#[derive(Debug, Clone)]
+pub struct AssetCache {
+    root: PathBuf,
+    max_bytes: u64,
+}
+
+impl AssetCache {
+    /// Open (and create if needed) a cache in `root`
+    ///
+    /// The size limit is read from the cache's settings file and defaults to
+    /// [`DEFAULT_MAX_BYTES`].
+    pub fn open(root: impl Into<PathBuf>) -> io::Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+        let max_bytes = std::fs::read_to_string(root.join(CONFIG_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str::<CacheConfig>(&json).ok())
+            .map_or(DEFAULT_MAX_BYTES, |config| config.max_bytes);
+        Ok(Self { root, max_bytes })
+    }
+
+    /// Open the cache in [`Self::default_dir`]
+    pub fn open_default() -> io::Result<Self> {
+        Self::open(Self::default_dir())
+    }
+
+    /// Cache directory from [`CACHE_DIR_ENV`], or [`DEFAULT_CACHE_DIR`]
+    pub fn default_dir() -> PathBuf {
+        std::env::var_os(CACHE_DIR_ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR))
+    }
+
+    /// Root directory of the cache
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Size limit in bytes
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Change and persist the size limit, evicting entries above it
+    pub fn set_max_bytes(&mut self, max_bytes: u64) -> io::Result<()> {
+        self.max_bytes = max_bytes;
+        let json = serde_json::to_string_pretty(&CacheConfig { max_bytes })
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(self.root.join(CONFIG_FILE), json)?;
+        self.trim_to(max_bytes)?;
+        Ok(())
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.root
+            .join(&key.kind)
+            .join(format!("{}.{}", key.hash, ENTRY_EXTENSION))
+    }
+
+    /// Whether an entry exists for `key`
+    pub fn contains(&self, key: &CacheKey) -> bool {
+        self.entry_path(key).is_file()
+    }
+
+    /// Read a cached entry, marking it as recently used
+    pub fn get(&self, key: &CacheKey) -> Option<Vec<u8>> {
+        let path = self.entry_path(key);
+        let data = std::fs::read(&path).ok()?;
+        if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        log::debug!("Asset cache hit: {}", key);
+        Some(data)
+    }
+
+    /// Store an entry and evict old entries if the cache grew over its limit
+    pub fn put(&self, key: &CacheKey, data: &[u8]) -> io::Result<()> {
+        let path = self.entry_path(key);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Write to a temporary file first so readers never see partial entries
+        let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, &path)?;
+        log::debug!("Asset cache store: {} ({} bytes)", key, data.len());
+
+        self.trim_to(self.max_bytes)?;
+        Ok(())
+    }
+
+    /// Return the cached entry for `key`, or compute and store it
+    ///
+    /// Failing to write the cache is logged but does not fail the call.
+    pub fn get_or_insert_with<E>(
+        &self,
+        key: &CacheKey,
+        compute: impl FnOnce() -> Result<Vec<u8>, E>,
+    ) -> Result<Vec<u8>, E> {
+        if let Some(data) = self.get(key) {
+            return Ok(data);
+        }
+        let data = compute()?;
+        if let Err(e) = self.put(key, &data) {
+            log::warn!("Failed to store {} in asset cache: {}", key, e);
+        }
+        Ok(data)
+    }
+
+    /// List all entries
+    pub fn entries(&self) -> io::Result<Vec<CacheEntryInfo>> {
+        let mut entries = Vec::new();
+        for kind_dir in std::fs::read_dir(&self.root)? {
+            let kind_dir = kind_dir?;
+            if !kind_dir.file_type()?.is_dir() {
+                continue;
+            }
+            let kind = kind_dir.file_name().to_string_lossy().into_owned();
+            for file in std::fs::read_dir(kind_dir.path())? {
+                let file = file?;
+                let path = file.path();
+                if path.extension().and_then(|e| e.to_str()) != Some(ENTRY_EXTENSION) {
+                    continue;
+                }
+                let metadata = file.metadata()?;
+                entries.push(CacheEntryInfo {
+                    kind: kind.clone(),
+                    path,
+                    size: metadata.len(),
+                    last_used: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                });
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Entry count and sizes
+    pub fn stats(&self) -> io::Result<CacheStats> {
+        let mut stats = CacheStats::default();
+        for entry in self.entries()? {
+            stats.entries += 1;
+            stats.total_bytes += entry.size;
+            let kind = stats.per_kind.entry(entry.kind).or_default();
+            kind.0 += 1;
+            kind.1 += entry.size;
+        }
+        Ok(stats)
+    }
+
+    /// Evict least recently used entries until the cache is at most `max_bytes`
+    ///
+    /// Returns the number of bytes freed.
+    pub fn trim_to(&self, max_bytes: u64) -> io::Result<u64> {
+        let mut entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|e| e.size).sum();
+        if total <= max_bytes {
+            return Ok(0);
+        }
+
+        entries.sort_by_key(|e| e.last_used);
+        let mut freed = 0;
+        for entry in entries {
+            if total <= max_bytes {
+                break;
+            }
+            std::fs::remove_file(&entry.path)?;
+            total -= entry.size;
+            freed += entry.size;
+        }
+        log::info!("Asset cache evicted {} bytes", freed);
+        Ok(freed)
+    }
+
+    /// Remove all entries of one kind
+    pub fn clear_kind(&self, kind: &str) -> io::Result<()> {
+        let dir = self.root.join(sanitize_kind(kind));
+        if dir.is_dir() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Remove all entries, keeping the settings
+    pub fn clear(&self) -> io::Result<()> {
+        for entry in std::fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                std::fs::remove_dir_all(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Format a byte count for display, e.g. `"1.5 MiB"`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str) -> AssetCache {
+        let dir = std::env::temp_dir().join(format!("wgpu_playground_asset_cache_{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        AssetCache::open(dir).unwrap()
+    }
+
+    #[test]
+    fn test_key_depends_on_content_and_params() {
+        let base = CacheKey::builder("mesh").content(b"abc").param("scale", 1);
+        let key = base.clone().build();
+        assert_eq!(key, base.clone().build());
+        assert_eq!(key.hash().len(), 32);
+        assert_ne!(key, CacheKey::builder("mesh").content(b"abd").build());
+        assert_ne!(key, base.param("flip", true).build());
+        assert_ne!(
+            CacheKey::builder("mesh")
+                .content(b"ab")
+                .content(b"c")
+                .build(),
+            CacheKey::builder("mesh")
+                .content(b"a")
+                .content(b"bc")
+                .build()
+        );
+        assert_eq!(
+            CacheKey::builder("../env maps").build().kind(),
+            "___env_maps"
+        );
+    }
+
+    #[test]
+    fn test_put_get_and_stats() {
+        let cache = temp_cache("put_get");
+        let key = CacheKey::builder("mips").content(b"image").build();
+        assert!(cache.get(&key).is_none());
+
+        cache.put(&key, &[1, 2, 3]).unwrap();
+        assert!(cache.contains(&key));
+        assert_eq!(cache.get(&key), Some(vec![1, 2, 3]));
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.total_bytes, 3);
+        assert_eq!(stats.per_kind.get("mips"), Some(&(1, 3)));
+
+        cache.clear().unwrap();
+        assert_eq!(cache.stats().unwrap(), CacheStats::default());
+        std::fs::remove_dir_all(cache.root()).ok();
+    }
+
+    #[test]
+    fn test_get_or_insert_with_computes_once() {
+        let cache = temp_cache("get_or_insert");
+        let key = CacheKey::builder("mesh").content(b"model").build();
+        let mut calls = 0;
+        for _ in 0..2 {
+            let data = cache
+                .get_or_insert_with(&key, || {
+                    calls += 1;
+                    Ok::<_, ()>(vec![7; 16])
+                })
+                .unwrap();
+            assert_eq!(data, vec![7; 16]);
+        }
+        assert_eq!(calls, 1);
+        std::fs::remove_dir_all(cache.root()).ok();
+    }
+
+    #[test]
+    fn test_size_limit_evicts_least_recently_used() {
+        let mut cache = temp_cache("evict");
+        let old = CacheKey::builder("mesh").content(b"old").build();
+        let new = CacheKey::builder("mesh").content(b"new").build();
+        cache.put(&old, &[0; 100]).unwrap();
+        let past = SystemTime::now() - std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .append(true)
+            .open(cache.entry_path(&old))
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+        cache.put(&new, &[0; 100]).unwrap();
+
+        cache.set_max_bytes(150).unwrap();
+        assert!(!cache.contains(&old));
+        assert!(cache.contains(&new));
+
+        // The limit is persisted with the cache
+        let reopened = AssetCache::open(cache.root()).unwrap();
+        assert_eq!(reopened.max_bytes(), 150);
+        std::fs::remove_dir_all(cache.root()).ok();
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(DEFAULT_MAX_BYTES), "512.0 MiB");
+    }
+}
@@ -0,0 +1,222 @@
+//! Off-thread asset loading with uploads spread across frames
+//!
+//! Image decodes and model imports run as [`JobSystem`] jobs, so the UI
+//! keeps drawing while they parse. Their results are then uploaded a few
+//! rows at a time: [`TextureUpload`] writes at most a per-frame byte budget
+//! of a decoded image into its texture, so a large image doesn't stall the
+//! frame that finishes decoding it either.
+
+use std::path::PathBuf;
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::asset_cache::AssetCache;
+use crate::job_system::{Job, JobSystem};
+use crate::ktx2;
+use crate::model_loader::{load_model_cached, load_model_from_file, ModelData, ModelLoadError};
+use crate::svg;
+
+/// Bytes of texture data uploaded per frame by default (4 MiB)
+pub const DEFAULT_UPLOAD_BUDGET: u64 = 4 * 1024 * 1024;
+
+/// An image decoded to 8-bit RGBA
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedImage {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major, 4 bytes per pixel
+    pub rgba: Vec<u8>,
+}
+
+/// Decode an encoded image (PNG, JPEG, ...) to RGBA
+pub fn decode_image(bytes: &[u8]) -> Result<DecodedImage, image::ImageError> {
+    let rgba = image::load_from_memory(bytes)?.to_rgba8();
+    Ok(DecodedImage {
+        width: rgba.width(),
+        height: rgba.height(),
+        rgba: rgba.into_raw(),
+    })
+}
+
+/// Decode `bytes` on the global job system
+///
+/// KTX2 textures are read with [`ktx2::decode`], SVGs rasterized at their
+/// intrinsic size and everything else decoded with [`decode_image`].
+pub fn spawn_image_decode(label: &str, bytes: Vec<u8>) -> Job<Result<DecodedImage, String>> {
+    JobSystem::global().spawn(label, move |progress| {
+        progress.set_stage("Decoding", 0.0);
+        let result = if ktx2::is_ktx2(&bytes) {
+            ktx2::decode(&bytes).map_err(|e| e.to_string())
+        } else if svg::is_svg(&bytes) {
+            svg::rasterize_intrinsic(&bytes).map_err(|e| e.to_string())
+        } else {
+            decode_image(&bytes).map_err(|e| e.to_string())
+        };
+        progress.set_stage("Decoded", 1.0);
+        result
+    })
+}
+
+/// Rasterize SVG `bytes` at `width` by `height` pixels on the global job
+/// system
+pub fn spawn_svg_rasterize(
+    label: &str,
+    bytes: Vec<u8>,
+    width: u32,
+    height: u32,
+) -> Job<Result<DecodedImage, String>> {
+    JobSystem::global().spawn(label, move |progress| {
+        progress.set_stage("Rasterizing", 0.0);
+        let result = svg::rasterize(&bytes, width, height).map_err(|e| e.to_string());
+        progress.set_stage("Rasterized", 1.0);
+        result
+    })
+}
+
+/// Import the model at `path` on the global job system, going through the
+/// default asset cache when it can be opened
+pub fn spawn_model_load(path: PathBuf) -> Job<Result<ModelData, ModelLoadError>> {
+    let label = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    JobSystem::global().spawn(&label, move |progress| {
+        progress.set_stage("Importing", 0.0);
+        let model = match AssetCache::open_default() {
+            Ok(cache) => load_model_cached(&path, &cache),
+            Err(e) => {
+                log::warn!("Asset cache unavailable: {}", e);
+                load_model_from_file(&path)
+            }
+        };
+        progress.set_stage("Imported", 1.0);
+        model
+    })
+}
+
+/// Rows of a `width` pixel wide RGBA image that fit in `budget` bytes,
+/// at least one
+pub fn rows_per_step(width: u32, budget: u64) -> u32 {
+    let row_bytes = 4 * width.max(1) as u64;
+    (budget / row_bytes).clamp(1, u32::MAX as u64) as u32
+}
+
+/// A decoded image being written into a texture over several frames
+///
+/// The texture must be 2D, `Rgba8Unorm` or `Rgba8UnormSrgb`, as large as
+/// the image and have `COPY_DST`. Rows not yet written read as zero.
+pub struct TextureUpload {
+    texture: wgpu::Texture,
+    image: DecodedImage,
+    /// First row not written yet
+    next_row: u32,
+}
+
+impl TextureUpload {
+    pub fn new(texture: wgpu::Texture, image: DecodedImage) -> Self {
+        Self {
+            texture,
+            image,
+            next_row: 0,
+        }
+    }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    pub fn image(&self) -> &DecodedImage {
+        &self.image
+    }
+
+    /// Write the next rows, up to `budget` bytes but at least one row
+    ///
+    /// Returns whether the whole image has been written.
+    pub fn step(&mut self, queue: &wgpu::Queue, budget: u64) -> bool {
+        if self.is_done() {
+            return true;
+        }
+        let DecodedImage {
+            width,
+            height,
+            ref rgba,
+        } = self.image;
+        let rows = rows_per_step(width, budget).min(height - self.next_row);
+        let row_bytes = 4 * width as usize;
+        let start = self.next_row as usize * row_bytes;
+
+        ApiCoverageTracker::global().record(ApiCategory::Queue, "write_texture");
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: 0,
+                    y: self.next_row,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &rgba[start..start + rows as usize * row_bytes],
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(rows),
+            },
+            wgpu::Extent3d {
+                width,
+                height: rows,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.next_row += rows;
+        self.is_done()
+    }
+
+    /// Write everything that is left
+    pub fn finish(&mut self, queue: &wgpu::Queue) {
+        self.step(queue, u64::MAX);
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next_row >= self.image.height
+    }
+
+    /// Fraction of the rows written, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        if self.image.height == 0 {
+            1.0
+        } else {
+            self.next_row as f32 / self.image.height as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_per_step() {
+        assert_eq!(rows_per_step(256, 4 * 256 * 10), 10);
+        assert_eq!(rows_per_step(256, 4 * 256 * 10 + 100), 10);
+        // A row wider than the budget is still written
+        assert_eq!(rows_per_step(4096, 100), 1);
+    }
+
+    #[test]
+    fn test_decode_image() {
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(3, 2, image::Rgba([10, 20, 30, 255]))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let decoded = spawn_image_decode("test.png", png).wait().unwrap().unwrap();
+        assert_eq!((decoded.width, decoded.height), (3, 2));
+        assert_eq!(&decoded.rgba[..4], &[10, 20, 30, 255]);
+
+        assert!(spawn_image_decode("broken.png", vec![0; 16])
+            .wait()
+            .unwrap()
+            .is_err());
+    }
+}
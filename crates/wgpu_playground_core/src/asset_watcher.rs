@@ -0,0 +1,221 @@
+use crate::assets::AssetKind;
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{channel, Receiver};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, Mutex};
+
+/// What happened to an asset file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetChangeKind {
+    /// File was created
+    Created,
+    /// File contents changed
+    Modified,
+    /// File was deleted
+    Removed,
+}
+
+/// Represents an asset file change event
+#[derive(Debug, Clone)]
+pub struct AssetChangeEvent {
+    /// Kind of asset, determined by its subdirectory
+    pub asset: AssetKind,
+    /// What happened to the file
+    pub change: AssetChangeKind,
+    /// The filename of the asset (e.g., "triangle.wgsl")
+    pub filename: String,
+    /// The full path to the asset file
+    pub path: PathBuf,
+}
+
+/// Classify a changed path below an assets root
+///
+/// Returns the asset kind and filename for files directly inside one of the
+/// `shaders/`, `textures/` or `models/` subdirectories.
+pub fn classify_path(root: &Path, path: &Path) -> Option<(AssetKind, String)> {
+    let relative = path.strip_prefix(root).ok()?;
+    let mut components = relative.components();
+    let subdir = components.next()?.as_os_str().to_str()?;
+    let filename = components.next()?.as_os_str().to_str()?.to_string();
+    if components.next().is_some() {
+        return None;
+    }
+    Some((AssetKind::from_subdir(subdir)?, filename))
+}
+
+/// Watches a whole assets directory (shaders, textures and models)
+///
+/// Unlike [`ShaderWatcher`](crate::shader_watcher::ShaderWatcher), this also
+/// reports created and removed files so that asset lists can be refreshed.
+/// On WASM platforms, the watcher can be created but will not detect any changes.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct AssetWatcher {
+    _watcher: notify::RecommendedWatcher,
+    receiver: Arc<Mutex<Receiver<AssetChangeEvent>>>,
+    root: PathBuf,
+}
+
+/// Type alias for the result type returned by AssetWatcher operations
+type WatcherResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AssetWatcher {
+    /// Watch the currently configured assets directory
+    pub fn new() -> WatcherResult<Self> {
+        Self::watch(crate::assets::assets_dir())
+    }
+
+    /// Watch a specific assets directory
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use wgpu_playground_core::asset_watcher::AssetWatcher;
+    ///
+    /// let watcher = AssetWatcher::watch("my_assets".into()).unwrap();
+    /// for event in watcher.poll_all() {
+    ///     println!("{:?} {:?}: {}", event.change, event.asset, event.filename);
+    /// }
+    /// ```
+    pub fn watch(root: PathBuf) -> WatcherResult<Self> {
+        use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+        let (tx, rx) = channel();
+        let tx = Arc::new(Mutex::new(tx));
+
+        log::info!("Starting asset watcher for directory: {:?}", root);
+
+        // Events may report either the path as given or its canonical form
+        let roots = [
+            root.clone(),
+            root.canonicalize().unwrap_or_else(|_| root.clone()),
+        ];
+        let mut watcher =
+            notify::recommended_watcher(move |res: Result<Event, notify::Error>| match res {
+                Ok(event) => {
+                    let change = match event.kind {
+                        EventKind::Create(_) => AssetChangeKind::Created,
+                        EventKind::Modify(_) => AssetChangeKind::Modified,
+                        EventKind::Remove(_) => AssetChangeKind::Removed,
+                        _ => return,
+                    };
+                    for path in event.paths {
+                        let classified = roots.iter().find_map(|r| classify_path(r, &path));
+                        if let Some((asset, filename)) = classified {
+                            log::info!("Detected asset change: {:?} {}", change, filename);
+                            if let Ok(tx) = tx.lock() {
+                                let _ = tx.send(AssetChangeEvent {
+                                    asset,
+                                    change,
+                                    filename,
+                                    path,
+                                });
+                            }
+                        }
+                    }
+                }
+                Err(e) => log::error!("Asset watcher error: {:?}", e),
+            })?;
+
+        watcher.watch(&root, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver: Arc::new(Mutex::new(rx)),
+            root,
+        })
+    }
+
+    /// Directory being watched
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Poll for an asset change event (non-blocking)
+    pub fn poll(&self) -> Option<AssetChangeEvent> {
+        if let Ok(rx) = self.receiver.lock() {
+            rx.try_recv().ok()
+        } else {
+            None
+        }
+    }
+
+    /// Get all pending asset change events
+    pub fn poll_all(&self) -> Vec<AssetChangeEvent> {
+        let mut events = Vec::new();
+        while let Some(event) = self.poll() {
+            events.push(event);
+        }
+        events
+    }
+}
+
+// WASM stub implementation
+/// WASM stub that provides the same API but without file watching functionality
+#[cfg(target_arch = "wasm32")]
+pub struct AssetWatcher {
+    root: PathBuf,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl AssetWatcher {
+    /// Create a new asset watcher (WASM stub - does nothing)
+    pub fn new() -> WatcherResult<Self> {
+        Self::watch(crate::assets::assets_dir())
+    }
+
+    /// Create a watcher for a directory (WASM stub - does nothing)
+    pub fn watch(root: PathBuf) -> WatcherResult<Self> {
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn poll(&self) -> Option<AssetChangeEvent> {
+        None
+    }
+
+    pub fn poll_all(&self) -> Vec<AssetChangeEvent> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_path() {
+        let root = Path::new("/data/assets");
+        assert_eq!(
+            classify_path(root, Path::new("/data/assets/shaders/fog.wgsl")),
+            Some((AssetKind::Shader, "fog.wgsl".to_string()))
+        );
+        assert_eq!(
+            classify_path(root, Path::new("/data/assets/textures/grass.png")),
+            Some((AssetKind::Texture, "grass.png".to_string()))
+        );
+        assert_eq!(
+            classify_path(root, Path::new("/data/assets/README.md")),
+            None
+        );
+        assert_eq!(
+            classify_path(root, Path::new("/data/assets/models/nested/a.obj")),
+            None
+        );
+        assert_eq!(
+            classify_path(root, Path::new("/elsewhere/shaders/a.wgsl")),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_asset_watcher_creation() {
+        let watcher = AssetWatcher::watch(crate::assets::builtin_assets_dir()).unwrap();
+        assert!(watcher.poll_all().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 /// Asset loading infrastructure for both native and web builds
 ///
@@ -32,12 +33,51 @@ fn validate_filename(filename: &str) -> Result<(), std::io::Error> {
     Ok(())
 }
 
-/// Get the base assets directory path
+/// Environment variable that points the playground at a user assets directory
+///
+/// Lets examples pick up customized shaders, textures and models without
+/// recompiling the crate.
+pub const ASSETS_DIR_ENV: &str = "WGPU_PLAYGROUND_ASSETS";
+
+/// User assets directory set at runtime, takes precedence over [`ASSETS_DIR_ENV`]
+static USER_ASSETS_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Kind of asset, which determines its subdirectory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    /// WGSL shaders in `shaders/`
+    Shader,
+    /// Images in `textures/`
+    Texture,
+    /// Meshes in `models/`
+    Model,
+}
+
+impl AssetKind {
+    /// All asset kinds
+    pub const ALL: [AssetKind; 3] = [AssetKind::Shader, AssetKind::Texture, AssetKind::Model];
+
+    /// Subdirectory of the assets directory holding this kind
+    pub fn subdir(&self) -> &'static str {
+        match self {
+            AssetKind::Shader => "shaders",
+            AssetKind::Texture => "textures",
+            AssetKind::Model => "models",
+        }
+    }
+
+    /// Asset kind for a subdirectory name
+    pub fn from_subdir(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.subdir() == name)
+    }
+}
+
+/// Get the assets directory bundled with the playground
 ///
 /// For native builds, this returns the path to the assets directory relative to the workspace root.
 /// For web builds, this returns a path suitable for web asset loading.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn assets_dir() -> PathBuf {
+pub fn builtin_assets_dir() -> PathBuf {
     // For native builds, assets are relative to the workspace root
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .parent()
@@ -47,24 +87,84 @@ pub fn assets_dir() -> PathBuf {
 }
 
 #[cfg(target_arch = "wasm32")]
-pub fn assets_dir() -> PathBuf {
+pub fn builtin_assets_dir() -> PathBuf {
     // For web builds, assets are served from the /assets path
     PathBuf::from("/assets")
 }
 
+/// Set (or with `None`, clear) the user assets directory at runtime
+pub fn set_user_assets_dir(dir: Option<PathBuf>) {
+    let mut user_dir = match USER_ASSETS_DIR.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    log::info!("User assets directory set to {:?}", dir);
+    *user_dir = dir;
+}
+
+/// Get the user assets directory, if one is configured
+///
+/// A directory set with [`set_user_assets_dir`] wins over [`ASSETS_DIR_ENV`].
+pub fn user_assets_dir() -> Option<PathBuf> {
+    let user_dir = match USER_ASSETS_DIR.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    user_dir.or_else(|| {
+        std::env::var_os(ASSETS_DIR_ENV)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// Get the base assets directory path
+///
+/// Returns the user assets directory if one is configured, otherwise the
+/// bundled one.
+pub fn assets_dir() -> PathBuf {
+    user_assets_dir().unwrap_or_else(builtin_assets_dir)
+}
+
 /// Get the path to the shaders directory
 pub fn shaders_dir() -> PathBuf {
-    assets_dir().join("shaders")
+    assets_dir().join(AssetKind::Shader.subdir())
 }
 
 /// Get the path to the textures directory
 pub fn textures_dir() -> PathBuf {
-    assets_dir().join("textures")
+    assets_dir().join(AssetKind::Texture.subdir())
 }
 
 /// Get the path to the models directory
 pub fn models_dir() -> PathBuf {
-    assets_dir().join("models")
+    assets_dir().join(AssetKind::Model.subdir())
+}
+
+/// Resolve an asset file
+///
+/// Files in the user assets directory override bundled files with the same
+/// name; anything the user directory lacks falls back to the bundled assets.
+///
+/// # Errors
+/// Returns an error if the filename is invalid
+pub fn resolve(kind: AssetKind, filename: &str) -> Result<PathBuf, std::io::Error> {
+    resolve_in(user_assets_dir().as_deref(), kind, filename)
+}
+
+/// [`resolve`] against an explicit user assets directory
+fn resolve_in(
+    user_dir: Option<&Path>,
+    kind: AssetKind,
+    filename: &str,
+) -> Result<PathBuf, std::io::Error> {
+    validate_filename(filename)?;
+    if let Some(user_dir) = user_dir {
+        let path = user_dir.join(kind.subdir()).join(filename);
+        if path.exists() {
+            return Ok(path);
+        }
+    }
+    Ok(builtin_assets_dir().join(kind.subdir()).join(filename))
 }
 
 /// Load a shader file from the shaders directory
@@ -78,8 +178,7 @@ pub fn models_dir() -> PathBuf {
 /// # Errors
 /// Returns an error if the file cannot be read or if the filename is invalid
 pub fn load_shader(filename: &str) -> Result<String, std::io::Error> {
-    validate_filename(filename)?;
-    let path = shaders_dir().join(filename);
+    let path = resolve(AssetKind::Shader, filename)?;
     load_string_from_path(&path)
 }
 
@@ -143,8 +242,7 @@ pub fn load_binary_from_path(_path: &Path) -> Result<Vec<u8>, std::io::Error> {
 /// # Errors
 /// Returns an error if the file cannot be read or if the filename is invalid
 pub fn load_texture(filename: &str) -> Result<Vec<u8>, std::io::Error> {
-    validate_filename(filename)?;
-    let path = textures_dir().join(filename);
+    let path = resolve(AssetKind::Texture, filename)?;
     load_binary_from_path(&path)
 }
 
@@ -159,8 +257,7 @@ pub fn load_texture(filename: &str) -> Result<Vec<u8>, std::io::Error> {
 /// # Errors
 /// Returns an error if the file cannot be read or if the filename is invalid
 pub fn load_model(filename: &str) -> Result<Vec<u8>, std::io::Error> {
-    validate_filename(filename)?;
-    let path = models_dir().join(filename);
+    let path = resolve(AssetKind::Model, filename)?;
     load_binary_from_path(&path)
 }
 
@@ -216,4 +313,40 @@ mod tests {
     fn test_validate_filename_empty() {
         assert!(validate_filename("").is_err());
     }
+
+    #[test]
+    fn test_asset_kind_subdir() {
+        for kind in AssetKind::ALL {
+            assert_eq!(AssetKind::from_subdir(kind.subdir()), Some(kind));
+        }
+        assert_eq!(AssetKind::from_subdir("sounds"), None);
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_builtin() {
+        let root = std::env::temp_dir().join(format!(
+            "wgpu_playground_user_assets_{}",
+            std::process::id()
+        ));
+        let user_dir = root.join("assets");
+        std::fs::create_dir_all(user_dir.join("shaders")).unwrap();
+        std::fs::write(user_dir.join("shaders").join("user_only.wgsl"), "// custom").unwrap();
+        let user = Some(user_dir.as_path());
+
+        assert_eq!(
+            resolve_in(user, AssetKind::Shader, "user_only.wgsl").unwrap(),
+            user_dir.join("shaders").join("user_only.wgsl")
+        );
+        assert_eq!(
+            resolve_in(user, AssetKind::Shader, "triangle.wgsl").unwrap(),
+            builtin_assets_dir().join("shaders").join("triangle.wgsl")
+        );
+        assert!(resolve_in(user, AssetKind::Shader, "../x.wgsl").is_err());
+        assert_eq!(
+            resolve_in(None, AssetKind::Shader, "user_only.wgsl").unwrap(),
+            builtin_assets_dir().join("shaders").join("user_only.wgsl")
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
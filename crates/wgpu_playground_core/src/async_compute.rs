@@ -0,0 +1,629 @@
+//! Simulated async compute on a single queue
+//!
+//! wgpu exposes one queue per device, so long compute work cannot run on a
+//! separate queue the way it would with Vulkan or D3D12 async compute. What
+//! an application can control is how the work is cut into submissions and
+//! how often the device is polled. [`AsyncComputeScheduler`] submits a long
+//! compute job as a configurable number of chunks, interleaved with a small
+//! "frame" submission per tick, and tracks every submission with
+//! [`wgpu::Queue::on_submitted_work_done`]. The recorded submit and
+//! completion times show how a frame stuck behind one large submission waits
+//! for all of it, and how callbacks are only delivered when the device is
+//! polled.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+
+/// Invocations per workgroup of the busy-loop shader
+pub const WORKGROUP_SIZE: u32 = 64;
+
+/// Workgroups dispatched by every submission
+pub const WORKGROUPS: u32 = 64;
+
+/// Number of submissions kept on the timeline
+pub const MAX_SUBMISSIONS: usize = 512;
+
+/// Size of the params uniform in bytes
+const PARAMS_SIZE: u64 = 16;
+
+const SHADER: &str = r#"
+struct Params {
+    iterations: u32,
+    seed: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> output: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    var x = id.x ^ params.seed;
+    for (var i = 0u; i < params.iterations; i++) {
+        x = x * 1664525u + 1013904223u;
+        x ^= x >> 16u;
+    }
+    output[id.x] = x;
+}
+"#;
+
+/// When the scheduler polls the device after submitting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollMode {
+    /// Block until all submitted work has finished
+    Wait,
+    /// Check for finished work once per tick without blocking
+    Poll,
+    /// Never poll; callbacks fire only when something else polls the device
+    Never,
+}
+
+impl PollMode {
+    pub const ALL: [PollMode; 3] = [PollMode::Wait, PollMode::Poll, PollMode::Never];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PollMode::Wait => "Wait",
+            PollMode::Poll => "Poll",
+            PollMode::Never => "Never",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PollMode::Wait => {
+                "device.poll(Wait) after every tick: callbacks arrive immediately, but the CPU stalls until the GPU is idle"
+            }
+            PollMode::Poll => {
+                "device.poll(Poll) once per tick: no stall, callbacks arrive up to a tick late"
+            }
+            PollMode::Never => {
+                "No explicit poll: callbacks wait until the next submit or another part of the app polls the device"
+            }
+        }
+    }
+}
+
+/// Which workload a submission belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    /// The small per-tick submission standing in for a rendered frame
+    Frame,
+    /// A chunk of the long compute job
+    Compute,
+}
+
+impl Lane {
+    pub const ALL: [Lane; 2] = [Lane::Frame, Lane::Compute];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lane::Frame => "Frame",
+            Lane::Compute => "Compute",
+        }
+    }
+}
+
+/// One tracked queue submission
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submission {
+    /// Submission order, starting at 0
+    pub id: u64,
+    pub lane: Lane,
+    /// Frame number for [`Lane::Frame`], job number for [`Lane::Compute`]
+    pub group: u64,
+    /// Chunk index within the job; always 0 for frames
+    pub chunk: u32,
+    /// Number of chunks in the job; always 1 for frames
+    pub chunks: u32,
+    /// Loop iterations per invocation
+    pub iterations: u32,
+    /// Time of `queue.submit`, relative to the scheduler's start
+    pub submitted: Duration,
+    /// Time the work-done callback ran
+    pub completed: Option<Duration>,
+    /// Order in which the callbacks ran, starting at 0
+    pub completion_order: Option<u64>,
+}
+
+impl Submission {
+    /// Time from submit to callback, once the callback has run
+    pub fn latency(&self) -> Option<Duration> {
+        self.completed
+            .map(|completed| completed.saturating_sub(self.submitted))
+    }
+}
+
+/// Latency summary of one lane
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LaneStats {
+    pub submitted: usize,
+    pub completed: usize,
+    pub mean_latency: Duration,
+    pub max_latency: Duration,
+}
+
+impl LaneStats {
+    pub fn from_submissions(submissions: &[Submission], lane: Lane) -> Self {
+        let mut stats = LaneStats::default();
+        let mut total = Duration::ZERO;
+        for submission in submissions.iter().filter(|s| s.lane == lane) {
+            stats.submitted += 1;
+            if let Some(latency) = submission.latency() {
+                stats.completed += 1;
+                total += latency;
+                stats.max_latency = stats.max_latency.max(latency);
+            }
+        }
+        if stats.completed > 0 {
+            stats.mean_latency = total / stats.completed as u32;
+        }
+        stats
+    }
+
+    /// Submissions whose callback has not run yet
+    pub fn in_flight(&self) -> usize {
+        self.submitted - self.completed
+    }
+}
+
+/// Time from the first chunk's submit to the last chunk's callback
+///
+/// None while any chunk of the job is still in flight or not yet submitted.
+pub fn job_latency(submissions: &[Submission], job: u64) -> Option<Duration> {
+    let chunks: Vec<&Submission> = submissions
+        .iter()
+        .filter(|s| s.lane == Lane::Compute && s.group == job)
+        .collect();
+    let first = chunks.iter().min_by_key(|s| s.chunk)?;
+    if chunks.len() != first.chunks as usize {
+        return None;
+    }
+    let completed = chunks
+        .iter()
+        .map(|s| s.completed)
+        .collect::<Option<Vec<_>>>()?;
+    let last = completed.into_iter().max()?;
+    Some(last.saturating_sub(first.submitted))
+}
+
+/// Split `total` iterations into `chunks` parts whose sizes differ by at
+/// most one
+pub fn split_iterations(total: u32, chunks: u32) -> Vec<u32> {
+    let chunks = chunks.max(1);
+    let base = total / chunks;
+    let remainder = total % chunks;
+    (0..chunks)
+        .map(|chunk| base + u32::from(chunk < remainder))
+        .collect()
+}
+
+/// Workload settings of the scheduler
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchedulerConfig {
+    /// Loop iterations per invocation of a whole compute job
+    pub job_iterations: u32,
+    /// Number of submissions the job is split into
+    pub chunks: u32,
+    /// Chunks submitted per tick
+    pub chunks_per_tick: u32,
+    /// Loop iterations per invocation of a frame submission
+    pub frame_iterations: u32,
+    pub poll_mode: PollMode,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            job_iterations: 400_000,
+            chunks: 8,
+            chunks_per_tick: 1,
+            frame_iterations: 2_000,
+            poll_mode: PollMode::Poll,
+        }
+    }
+}
+
+/// Submission records shared with the work-done callbacks
+#[derive(Default)]
+struct Timeline {
+    submissions: VecDeque<Submission>,
+    next_completion: u64,
+}
+
+impl Timeline {
+    fn push(&mut self, submission: Submission) {
+        if self.submissions.len() == MAX_SUBMISSIONS {
+            self.submissions.pop_front();
+        }
+        self.submissions.push_back(submission);
+    }
+
+    fn complete(&mut self, id: u64, at: Duration) {
+        let order = self.next_completion;
+        self.next_completion += 1;
+        if let Some(submission) = self.submissions.iter_mut().find(|s| s.id == id) {
+            submission.completed = Some(at);
+            submission.completion_order = Some(order);
+        }
+    }
+}
+
+/// Submits chunked compute jobs and frame work on one queue and records
+/// when each submission finishes
+pub struct AsyncComputeScheduler {
+    config: SchedulerConfig,
+    epoch: Instant,
+    timeline: Arc<Mutex<Timeline>>,
+    next_id: u64,
+    frame: u64,
+    next_job: u64,
+    /// Chunks of started jobs that are not submitted yet: (job, chunk, chunks, iterations)
+    pending: VecDeque<(u64, u32, u32, u32)>,
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    params_buffer: wgpu::Buffer,
+}
+
+impl AsyncComputeScheduler {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Async Compute Shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Async Compute Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Async Compute Pipeline Layout"),
+            bind_group_layouts: &[Some(&layout)],
+            immediate_size: 0,
+        });
+
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Async Compute Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Async Compute Params"),
+            size: PARAMS_SIZE,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Async Compute Output"),
+            size: (WORKGROUPS * WORKGROUP_SIZE) as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Async Compute Bind Group"),
+            layout: &layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            config: SchedulerConfig::default(),
+            epoch: Instant::now(),
+            timeline: Arc::new(Mutex::new(Timeline::default())),
+            next_id: 0,
+            frame: 0,
+            next_job: 0,
+            pending: VecDeque::new(),
+            pipeline,
+            bind_group,
+            params_buffer,
+        }
+    }
+
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.config
+    }
+
+    /// Change the settings; jobs that already started keep their chunking
+    pub fn set_config(&mut self, config: SchedulerConfig) {
+        self.config = SchedulerConfig {
+            chunks: config.chunks.max(1),
+            chunks_per_tick: config.chunks_per_tick.max(1),
+            ..config
+        };
+    }
+
+    /// Time since the scheduler was created or cleared
+    pub fn elapsed(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+
+    /// Queue a new compute job; its chunks are submitted by the next ticks
+    ///
+    /// Returns the job number.
+    pub fn start_job(&mut self) -> u64 {
+        let job = self.next_job;
+        self.next_job += 1;
+        let parts = split_iterations(self.config.job_iterations, self.config.chunks);
+        let chunks = parts.len() as u32;
+        for (chunk, iterations) in parts.into_iter().enumerate() {
+            self.pending
+                .push_back((job, chunk as u32, chunks, iterations));
+        }
+        job
+    }
+
+    /// Chunks waiting to be submitted
+    pub fn pending_chunks(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Submit the next job chunks and one frame, then poll as configured
+    pub fn tick(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        for _ in 0..self.config.chunks_per_tick {
+            let Some((job, chunk, chunks, iterations)) = self.pending.pop_front() else {
+                break;
+            };
+            self.submit(device, queue, Lane::Compute, job, chunk, chunks, iterations);
+        }
+
+        let frame = self.frame;
+        self.frame += 1;
+        self.submit(
+            device,
+            queue,
+            Lane::Frame,
+            frame,
+            0,
+            1,
+            self.config.frame_iterations,
+        );
+
+        match self.config.poll_mode {
+            PollMode::Wait => self.wait_idle(device),
+            PollMode::Poll => {
+                let _ = device.poll(wgpu::PollType::Poll);
+            }
+            PollMode::Never => {}
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn submit(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        lane: Lane,
+        group: u64,
+        chunk: u32,
+        chunks: u32,
+        iterations: u32,
+    ) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        // write_buffer lands before the next submit, so every submission
+        // sees its own iteration count
+        let seed = id as u32;
+        queue.write_buffer(
+            &self.params_buffer,
+            0,
+            bytemuck::cast_slice(&[iterations, seed, 0, 0]),
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some(match lane {
+                Lane::Frame => "Async Compute Frame Encoder",
+                Lane::Compute => "Async Compute Job Encoder",
+            }),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Async Compute Pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.dispatch_workgroups(WORKGROUPS, 1, 1);
+        }
+
+        let mut timeline = self.timeline.lock().unwrap_or_else(|e| e.into_inner());
+        timeline.push(Submission {
+            id,
+            lane,
+            group,
+            chunk,
+            chunks,
+            iterations,
+            submitted: self.epoch.elapsed(),
+            completed: None,
+            completion_order: None,
+        });
+        drop(timeline);
+
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Queue, "on_submitted_work_done");
+        let timeline = self.timeline.clone();
+        let epoch = self.epoch;
+        queue.on_submitted_work_done(move || {
+            timeline
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .complete(id, epoch.elapsed());
+        });
+    }
+
+    /// Block until every submission so far has finished and its callback ran
+    pub fn wait_idle(&self, device: &wgpu::Device) {
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+    }
+
+    /// The recorded submissions, oldest first
+    pub fn submissions(&self) -> Vec<Submission> {
+        self.timeline
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .submissions
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Forget the recorded submissions and pending chunks and restart the
+    /// clock
+    ///
+    /// Callbacks of submissions still in flight are ignored.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.epoch = Instant::now();
+        self.timeline = Arc::new(Mutex::new(Timeline::default()));
+        self.next_id = 0;
+        self.frame = 0;
+        self.next_job = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(id: u64, lane: Lane, group: u64, chunk: u32, chunks: u32) -> Submission {
+        Submission {
+            id,
+            lane,
+            group,
+            chunk,
+            chunks,
+            iterations: 1,
+            submitted: Duration::from_millis(id * 10),
+            completed: None,
+            completion_order: None,
+        }
+    }
+
+    #[test]
+    fn test_split_iterations() {
+        assert_eq!(split_iterations(10, 3), vec![4, 3, 3]);
+        assert_eq!(split_iterations(8, 4), vec![2, 2, 2, 2]);
+        assert_eq!(split_iterations(5, 0), vec![5]);
+        assert_eq!(split_iterations(10, 3).iter().sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_timeline_records_completion_order() {
+        let mut timeline = Timeline::default();
+        timeline.push(submission(0, Lane::Compute, 0, 0, 1));
+        timeline.push(submission(1, Lane::Frame, 0, 0, 1));
+        timeline.complete(1, Duration::from_millis(30));
+        timeline.complete(0, Duration::from_millis(40));
+
+        assert_eq!(timeline.submissions[0].completion_order, Some(1));
+        assert_eq!(timeline.submissions[1].completion_order, Some(0));
+        assert_eq!(
+            timeline.submissions[1].latency(),
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn test_timeline_drops_oldest() {
+        let mut timeline = Timeline::default();
+        for id in 0..MAX_SUBMISSIONS as u64 + 3 {
+            timeline.push(submission(id, Lane::Frame, id, 0, 1));
+        }
+        assert_eq!(timeline.submissions.len(), MAX_SUBMISSIONS);
+        assert_eq!(timeline.submissions[0].id, 3);
+        // Completing a dropped submission is harmless
+        timeline.complete(0, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_lane_stats_and_job_latency() {
+        let mut submissions = vec![
+            submission(0, Lane::Compute, 0, 0, 2),
+            submission(1, Lane::Frame, 0, 0, 1),
+            submission(2, Lane::Compute, 0, 1, 2),
+            submission(3, Lane::Frame, 1, 0, 1),
+        ];
+        submissions[0].completed = Some(Duration::from_millis(15));
+        submissions[1].completed = Some(Duration::from_millis(20));
+        assert_eq!(job_latency(&submissions, 0), None);
+
+        submissions[2].completed = Some(Duration::from_millis(50));
+        assert_eq!(
+            job_latency(&submissions, 0),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(job_latency(&submissions, 1), None);
+
+        let frames = LaneStats::from_submissions(&submissions, Lane::Frame);
+        assert_eq!(frames.submitted, 2);
+        assert_eq!(frames.completed, 1);
+        assert_eq!(frames.in_flight(), 1);
+        assert_eq!(frames.mean_latency, Duration::from_millis(10));
+
+        let compute = LaneStats::from_submissions(&submissions, Lane::Compute);
+        assert_eq!(
+            compute.mean_latency,
+            Duration::from_millis(22) + Duration::from_micros(500)
+        );
+        assert_eq!(compute.max_latency, Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_shader_validates() {
+        let module = naga::front::wgsl::parse_str(SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+}
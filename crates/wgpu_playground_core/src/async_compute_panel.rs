@@ -0,0 +1,398 @@
+use std::time::Duration;
+
+use egui::{Color32, Pos2, Rect, Vec2};
+
+use crate::async_compute::{
+    job_latency, AsyncComputeScheduler, Lane, LaneStats, PollMode, SchedulerConfig, Submission,
+};
+use crate::device_recovery::ResourceRecreate;
+
+/// Height of one lane on the timeline
+const LANE_HEIGHT: f32 = 36.0;
+/// Width of the lane labels left of the timeline
+const LABEL_WIDTH: f32 = 70.0;
+/// Height of the time axis below the lanes
+const AXIS_HEIGHT: f32 = 18.0;
+
+/// Horizontal position of `time` on a timeline that ends at `now` and
+/// spans `span`, clamped to the rect
+pub fn timeline_x(time: Duration, now: Duration, span: Duration, rect: Rect) -> f32 {
+    let start = now.saturating_sub(span).as_secs_f32();
+    let t = (time.as_secs_f32() - start) / span.as_secs_f32().max(f32::EPSILON);
+    rect.left() + t.clamp(0.0, 1.0) * rect.width()
+}
+
+fn lane_color(lane: Lane, index: u64) -> Color32 {
+    let base = match lane {
+        Lane::Frame => Color32::from_rgb(100, 150, 255),
+        Lane::Compute => Color32::from_rgb(255, 150, 100),
+    };
+    // Alternate shades so neighbouring submissions stay distinguishable
+    if index % 2 == 0 {
+        base
+    } else {
+        base.linear_multiply(0.7)
+    }
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// UI panel that schedules chunked compute jobs next to per-frame work and
+/// draws when each submission finished
+pub struct AsyncComputePanel {
+    config: SchedulerConfig,
+    running: bool,
+    /// Seconds shown on the timeline
+    span: f32,
+    scheduler: Option<AsyncComputeScheduler>,
+}
+
+impl Default for AsyncComputePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsyncComputePanel {
+    pub fn new() -> Self {
+        Self {
+            config: SchedulerConfig::default(),
+            running: false,
+            span: 1.0,
+            scheduler: None,
+        }
+    }
+
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: SchedulerConfig) {
+        self.config = config;
+    }
+
+    pub fn set_running(&mut self, running: bool) {
+        self.running = running;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn scheduler(&self) -> Option<&AsyncComputeScheduler> {
+        self.scheduler.as_ref()
+    }
+
+    /// Run one scheduler tick, creating the scheduler on first use
+    pub fn tick(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let scheduler = self
+            .scheduler
+            .get_or_insert_with(|| AsyncComputeScheduler::new(device));
+        scheduler.set_config(self.config);
+        scheduler.tick(device, queue);
+    }
+
+    /// Queue a compute job with the current settings
+    pub fn start_job(&mut self, device: &wgpu::Device) -> u64 {
+        let scheduler = self
+            .scheduler
+            .get_or_insert_with(|| AsyncComputeScheduler::new(device));
+        scheduler.set_config(self.config);
+        scheduler.start_job()
+    }
+
+    /// Render the panel, ticking the scheduler once per frame while running
+    pub fn ui(&mut self, ui: &mut egui::Ui, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.running {
+            self.tick(device, queue);
+            ui.ctx().request_repaint();
+        }
+
+        ui.heading("⏱ Async Compute");
+        ui.separator();
+        ui.label(
+            "wgpu has a single queue, so long compute work shares it with every frame. \
+             Split a job into more submissions and change how the device is polled to see \
+             how frame latency and job latency trade off.",
+        );
+        ui.add_space(10.0);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let label = if self.running { "⏸ Pause" } else { "▶ Run" };
+                if ui.button(label).clicked() {
+                    self.running = !self.running;
+                }
+                if ui.button("🚀 Start Job").clicked() {
+                    self.start_job(device);
+                    self.running = true;
+                }
+                if ui.button("🗑 Clear").clicked() {
+                    if let Some(scheduler) = &mut self.scheduler {
+                        scheduler.clear();
+                    }
+                }
+            });
+            ui.add_space(5.0);
+
+            egui::Grid::new("async_compute_settings")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Job iterations:");
+                    ui.add(
+                        egui::Slider::new(&mut self.config.job_iterations, 10_000..=2_000_000)
+                            .logarithmic(true),
+                    )
+                    .on_hover_text("Loop iterations per invocation for the whole job");
+                    ui.end_row();
+
+                    ui.label("Chunks per job:");
+                    ui.add(egui::Slider::new(&mut self.config.chunks, 1..=64))
+                        .on_hover_text("Number of submissions the job is split into");
+                    ui.end_row();
+
+                    ui.label("Chunks per tick:");
+                    ui.add(egui::Slider::new(&mut self.config.chunks_per_tick, 1..=64))
+                        .on_hover_text("Job submissions made before each frame submission");
+                    ui.end_row();
+
+                    ui.label("Frame iterations:");
+                    ui.add(
+                        egui::Slider::new(&mut self.config.frame_iterations, 100..=100_000)
+                            .logarithmic(true),
+                    );
+                    ui.end_row();
+
+                    ui.label("Poll mode:");
+                    ui.horizontal(|ui| {
+                        for mode in PollMode::ALL {
+                            ui.selectable_value(&mut self.config.poll_mode, mode, mode.name())
+                                .on_hover_text(mode.description());
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Timeline span:");
+                    ui.add(egui::Slider::new(&mut self.span, 0.1..=5.0).suffix(" s"));
+                    ui.end_row();
+                });
+            ui.label(
+                egui::RichText::new(self.config.poll_mode.description())
+                    .small()
+                    .weak(),
+            );
+            ui.add_space(10.0);
+
+            let Some(scheduler) = &self.scheduler else {
+                ui.label("Run the scheduler or start a job to record submissions.");
+                return;
+            };
+            let submissions = scheduler.submissions();
+            let now = scheduler.elapsed();
+
+            self.render_stats(ui, scheduler, &submissions);
+            ui.add_space(10.0);
+            self.render_timeline(ui, &submissions, now);
+        });
+    }
+
+    fn render_stats(
+        &self,
+        ui: &mut egui::Ui,
+        scheduler: &AsyncComputeScheduler,
+        submissions: &[Submission],
+    ) {
+        egui::Grid::new("async_compute_stats")
+            .num_columns(5)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("Lane").strong());
+                ui.label(egui::RichText::new("Submitted").strong());
+                ui.label(egui::RichText::new("In flight").strong());
+                ui.label(egui::RichText::new("Mean latency").strong());
+                ui.label(egui::RichText::new("Max latency").strong());
+                ui.end_row();
+
+                for lane in Lane::ALL {
+                    let stats = LaneStats::from_submissions(submissions, lane);
+                    ui.label(lane.name());
+                    ui.monospace(stats.submitted.to_string());
+                    ui.monospace(stats.in_flight().to_string());
+                    ui.monospace(format_ms(stats.mean_latency));
+                    ui.monospace(format_ms(stats.max_latency));
+                    ui.end_row();
+                }
+            });
+
+        let last_job = submissions
+            .iter()
+            .rev()
+            .find(|s| s.lane == Lane::Compute)
+            .map(|s| s.group);
+        ui.horizontal(|ui| {
+            ui.label(format!("Pending chunks: {}", scheduler.pending_chunks()));
+            if let Some(job) = last_job {
+                ui.separator();
+                match job_latency(submissions, job) {
+                    Some(latency) => ui.label(format!("Job {} took {}", job, format_ms(latency))),
+                    None => ui.label(format!("Job {} in progress", job)),
+                };
+            }
+        });
+    }
+
+    fn render_timeline(&self, ui: &mut egui::Ui, submissions: &[Submission], now: Duration) {
+        let span = Duration::from_secs_f32(self.span);
+        let height = LANE_HEIGHT * Lane::ALL.len() as f32 + AXIS_HEIGHT;
+        let (response, painter) = ui.allocate_painter(
+            Vec2::new(ui.available_width(), height),
+            egui::Sense::hover(),
+        );
+        let rect = response.rect;
+        painter.rect_filled(rect, 0.0, Color32::from_rgb(30, 30, 35));
+
+        let track = Rect::from_min_max(
+            Pos2::new(rect.left() + LABEL_WIDTH, rect.top()),
+            Pos2::new(rect.right() - 4.0, rect.bottom() - AXIS_HEIGHT),
+        );
+
+        for (row, lane) in Lane::ALL.iter().enumerate() {
+            let y = track.top() + row as f32 * LANE_HEIGHT;
+            painter.text(
+                Pos2::new(rect.left() + 6.0, y + LANE_HEIGHT / 2.0),
+                egui::Align2::LEFT_CENTER,
+                lane.name(),
+                egui::FontId::proportional(13.0),
+                Color32::LIGHT_GRAY,
+            );
+            painter.hline(
+                track.x_range(),
+                y + LANE_HEIGHT,
+                egui::Stroke::new(1.0, Color32::from_gray(60)),
+            );
+        }
+
+        // Axis ticks every tenth of the span, labelled relative to now
+        for tick in 0..=10 {
+            let x = track.left() + track.width() * tick as f32 / 10.0;
+            painter.vline(
+                x,
+                track.y_range(),
+                egui::Stroke::new(1.0, Color32::from_gray(45)),
+            );
+            if tick % 2 == 0 {
+                let ago = self.span * (10 - tick) as f32 / 10.0;
+                painter.text(
+                    Pos2::new(x, track.bottom() + AXIS_HEIGHT / 2.0),
+                    egui::Align2::CENTER_CENTER,
+                    format!("-{:.0} ms", ago * 1000.0),
+                    egui::FontId::proportional(10.0),
+                    Color32::GRAY,
+                );
+            }
+        }
+
+        let hover = response.hover_pos();
+        let mut hovered = None;
+        for submission in submissions {
+            let end = submission.completed.unwrap_or(now);
+            if end + span < now {
+                continue;
+            }
+            let row = Lane::ALL
+                .iter()
+                .position(|lane| *lane == submission.lane)
+                .unwrap_or(0);
+            let y = track.top() + row as f32 * LANE_HEIGHT;
+            let left = timeline_x(submission.submitted, now, span, track);
+            let right = timeline_x(end, now, span, track).max(left + 2.0);
+            let bar = Rect::from_min_max(
+                Pos2::new(left, y + 6.0),
+                Pos2::new(right, y + LANE_HEIGHT - 6.0),
+            );
+
+            let index = match submission.lane {
+                Lane::Frame => submission.group,
+                Lane::Compute => submission.chunk as u64,
+            };
+            let color = lane_color(submission.lane, index);
+            if submission.completed.is_some() {
+                painter.rect_filled(bar, 2.0, color);
+            } else {
+                // Still in flight: outline only
+                painter.rect_stroke(
+                    bar,
+                    2.0,
+                    egui::Stroke::new(1.0, color),
+                    egui::epaint::StrokeKind::Inside,
+                );
+            }
+            if hover.is_some_and(|pos| bar.contains(pos)) {
+                hovered = Some(submission);
+            }
+        }
+
+        if let Some(submission) = hovered {
+            let mut text = match submission.lane {
+                Lane::Frame => format!("Frame {}", submission.group),
+                Lane::Compute => format!(
+                    "Job {} chunk {}/{}",
+                    submission.group,
+                    submission.chunk + 1,
+                    submission.chunks
+                ),
+            };
+            text.push_str(&format!(
+                "\nSubmission #{}, {} iterations",
+                submission.id, submission.iterations
+            ));
+            match (submission.latency(), submission.completion_order) {
+                (Some(latency), Some(order)) => text.push_str(&format!(
+                    "\nFinished in {} (callback #{})",
+                    format_ms(latency),
+                    order
+                )),
+                _ => text.push_str("\nIn flight"),
+            }
+            response.on_hover_text(text);
+        }
+    }
+}
+
+impl ResourceRecreate for AsyncComputePanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // The pipeline belongs to the old device; the scheduler is created
+        // again on the next tick
+        self.scheduler = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_x() {
+        let rect = Rect::from_min_size(Pos2::new(10.0, 0.0), Vec2::new(100.0, 20.0));
+        let now = Duration::from_secs(3);
+        let span = Duration::from_secs(2);
+        assert_eq!(timeline_x(Duration::from_secs(1), now, span, rect), 10.0);
+        assert_eq!(timeline_x(Duration::from_secs(2), now, span, rect), 60.0);
+        assert_eq!(timeline_x(now, now, span, rect), 110.0);
+        // Older and future times are clamped to the edges
+        assert_eq!(timeline_x(Duration::ZERO, now, span, rect), 10.0);
+        assert_eq!(timeline_x(Duration::from_secs(4), now, span, rect), 110.0);
+    }
+
+    #[test]
+    fn test_new_panel_is_idle() {
+        let panel = AsyncComputePanel::new();
+        assert!(!panel.is_running());
+        assert!(panel.scheduler().is_none());
+        assert_eq!(*panel.config(), SchedulerConfig::default());
+    }
+}
@@ -0,0 +1,742 @@
+//! Audio input for audio-reactive shaders
+//!
+//! Samples from a microphone or loopback device (captured with cpal when
+//! built with the `audio` feature) or from a built-in test signal go
+//! through an FFT once per frame. The analysis gives:
+//!
+//! - 16 spectrum bands, spaced logarithmically from 20 Hz to 20 kHz
+//! - overall level, bass, mid and treble loudness
+//! - a beat value that jumps to 1 when the bass energy spikes above its
+//!   recent average and decays towards 0
+//!
+//! Everything is mapped from -60..0 dB to 0..1, rising at once and
+//! falling off smoothly, so it can drive a shader directly. Loopback
+//! capture depends on the platform: PulseAudio and PipeWire list
+//! "Monitor of ..." sources next to the microphones.
+
+use std::collections::VecDeque;
+use std::f32::consts::TAU;
+use std::ops::Range;
+
+/// Samples per FFT
+pub const FFT_SIZE: usize = 1024;
+
+/// Number of spectrum bands
+pub const BAND_COUNT: usize = 16;
+
+/// Sample rate of the test signal
+pub const TEST_SIGNAL_RATE: f32 = 48_000.0;
+
+/// Frequency range the bands cover, in Hz
+const LOWEST_FREQUENCY: f32 = 20.0;
+const HIGHEST_FREQUENCY: f32 = 20_000.0;
+
+/// Loudness mapped to 0
+const FLOOR_DB: f32 = -60.0;
+
+/// How fast values fall back after a peak, per second
+const RELEASE_RATE: f32 = 6.0;
+
+/// Bands averaged into the bass, mid and treble values
+const BASS_BANDS: Range<usize> = 0..4;
+const MID_BANDS: Range<usize> = 4..10;
+const TREBLE_BANDS: Range<usize> = 10..BAND_COUNT;
+
+/// Frequencies below this make up the energy beats are detected in
+const BEAT_FREQUENCY: f32 = 150.0;
+/// Frames of bass energy a beat is compared against, about 0.7 s
+const BEAT_HISTORY: usize = 43;
+/// How far above the recent average the energy has to jump
+const BEAT_THRESHOLD: f32 = 1.5;
+/// Shortest time between two beats, in seconds
+const BEAT_COOLDOWN: f32 = 0.2;
+/// How fast the beat value falls back, per second
+const BEAT_DECAY: f32 = 8.0;
+
+/// Error starting audio capture
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioError {
+    /// Built without the `audio` feature, or running on the web
+    Unsupported,
+    /// No input device has this name
+    DeviceNotFound(String),
+    /// The device could not be queried
+    Device(String),
+    /// The capture stream could not be started
+    Stream(String),
+}
+
+impl std::fmt::Display for AudioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioError::Unsupported => write!(
+                f,
+                "Audio capture needs a native build with the `audio` feature"
+            ),
+            AudioError::DeviceNotFound(name) => write!(f, "No audio input named '{}'", name),
+            AudioError::Device(msg) => write!(f, "Audio device error: {}", msg),
+            AudioError::Stream(msg) => write!(f, "Audio stream error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
+
+/// In-place radix-2 FFT of the complex signal `re + i * im`
+///
+/// # Panics
+///
+/// If the length isn't a power of two or the slices differ in length.
+pub fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    assert!(n.is_power_of_two() && im.len() == n);
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -TAU / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * cos - im[b] * sin;
+                let t_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Band edges in Hz, spaced logarithmically up to the Nyquist frequency
+pub fn band_edges(sample_rate: f32) -> [f32; BAND_COUNT + 1] {
+    let highest = HIGHEST_FREQUENCY.min(sample_rate / 2.0);
+    let ratio = highest / LOWEST_FREQUENCY;
+    std::array::from_fn(|i| LOWEST_FREQUENCY * ratio.powf(i as f32 / BAND_COUNT as f32))
+}
+
+/// Map an amplitude, 1 being full scale, from -60..0 dB to 0..1
+fn to_unit(amplitude: f32) -> f32 {
+    let db = 20.0 * amplitude.max(1e-9).log10();
+    ((db - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0)
+}
+
+/// What the analysis of a frame of audio found, every value in 0..1
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AudioFeatures {
+    /// Loudness of the spectrum bands, low to high frequencies
+    pub bands: [f32; BAND_COUNT],
+    pub level: f32,
+    pub bass: f32,
+    pub mid: f32,
+    pub treble: f32,
+    /// 1 on a beat, decaying towards 0
+    pub beat: f32,
+}
+
+impl AudioFeatures {
+    /// Level, bass, mid and treble, as the shader's `iAudio`
+    pub fn summary(&self) -> [f32; 4] {
+        [self.level, self.bass, self.mid, self.treble]
+    }
+}
+
+/// Spectrum, loudness and beat analysis of a mono signal
+#[derive(Debug, Clone)]
+pub struct AudioAnalyzer {
+    sample_rate: f32,
+    /// The last [`FFT_SIZE`] samples
+    samples: VecDeque<f32>,
+    /// Hann window
+    window: Vec<f32>,
+    features: AudioFeatures,
+    /// Bass energy of the last frames
+    bass_history: VecDeque<f32>,
+    since_beat: f32,
+    beats: u64,
+}
+
+impl AudioAnalyzer {
+    pub fn new(sample_rate: f32) -> Self {
+        let window = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (TAU * i as f32 / FFT_SIZE as f32).cos())
+            .collect();
+        Self {
+            sample_rate,
+            samples: VecDeque::from(vec![0.0; FFT_SIZE]),
+            window,
+            features: AudioFeatures::default(),
+            bass_history: VecDeque::with_capacity(BEAT_HISTORY),
+            since_beat: 0.0,
+            beats: 0,
+        }
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    /// Switch to a signal with a different sample rate, starting over
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        *self = Self::new(sample_rate);
+    }
+
+    /// Forget the signal and bring every value back to zero
+    pub fn reset(&mut self) {
+        self.set_sample_rate(self.sample_rate);
+    }
+
+    /// Append mono samples, keeping the last [`FFT_SIZE`]
+    pub fn push_samples(&mut self, samples: &[f32]) {
+        let skip = samples.len().saturating_sub(FFT_SIZE);
+        for &sample in &samples[skip..] {
+            self.samples.pop_front();
+            self.samples.push_back(sample);
+        }
+    }
+
+    pub fn features(&self) -> &AudioFeatures {
+        &self.features
+    }
+
+    /// Beats detected since the start
+    pub fn beats(&self) -> u64 {
+        self.beats
+    }
+
+    /// Analyze the last [`FFT_SIZE`] samples, `dt` seconds after the
+    /// previous analysis
+    pub fn analyze(&mut self, dt: f32) -> &AudioFeatures {
+        let mut re: Vec<f32> = self
+            .samples
+            .iter()
+            .zip(&self.window)
+            .map(|(sample, window)| sample * window)
+            .collect();
+        let mut im = vec![0.0; FFT_SIZE];
+        fft(&mut re, &mut im);
+
+        // A full-scale sine comes out at 1; the Hann window halves it
+        let scale = 4.0 / FFT_SIZE as f32;
+        let amplitudes: Vec<f32> = (0..FFT_SIZE / 2)
+            .map(|k| re[k].hypot(im[k]) * scale)
+            .collect();
+        let bin_width = self.sample_rate / FFT_SIZE as f32;
+        let bin = |frequency: f32| ((frequency / bin_width) as usize).min(FFT_SIZE / 2 - 1);
+
+        let release = (-RELEASE_RATE * dt).exp();
+        let features = &mut self.features;
+        let edges = band_edges(self.sample_rate);
+        for (band, value) in features.bands.iter_mut().enumerate() {
+            // Low bands narrower than a bin share it with their neighbours
+            let low = bin(edges[band]);
+            let high = bin(edges[band + 1]).max(low + 1);
+            let peak = amplitudes[low..high].iter().copied().fold(0.0, f32::max);
+            *value = to_unit(peak).max(*value * release);
+        }
+
+        let rms = (self.samples.iter().map(|s| s * s).sum::<f32>() / FFT_SIZE as f32).sqrt();
+        features.level = to_unit(rms * std::f32::consts::SQRT_2).max(features.level * release);
+        let mean = |bands: &[f32]| bands.iter().sum::<f32>() / bands.len() as f32;
+        features.bass = mean(&features.bands[BASS_BANDS]);
+        features.mid = mean(&features.bands[MID_BANDS]);
+        features.treble = mean(&features.bands[TREBLE_BANDS]);
+
+        // A beat is a jump of the bass energy above its recent average
+        let energy = amplitudes[1..=bin(BEAT_FREQUENCY)]
+            .iter()
+            .map(|a| a * a)
+            .sum::<f32>();
+        let average = if self.bass_history.is_empty() {
+            f32::INFINITY
+        } else {
+            self.bass_history.iter().sum::<f32>() / self.bass_history.len() as f32
+        };
+        self.since_beat += dt;
+        if energy > BEAT_THRESHOLD * average && energy > 1e-4 && self.since_beat > BEAT_COOLDOWN {
+            features.beat = 1.0;
+            self.since_beat = 0.0;
+            self.beats += 1;
+        } else {
+            features.beat *= (-BEAT_DECAY * dt).exp();
+        }
+        if self.bass_history.len() == BEAT_HISTORY {
+            self.bass_history.pop_front();
+        }
+        self.bass_history.push_back(energy);
+
+        &self.features
+    }
+}
+
+/// Synthetic input: a kick drum on every beat over a slow sine sweep
+#[derive(Debug, Clone)]
+pub struct TestSignal {
+    pub bpm: f32,
+    /// Samples generated so far
+    position: u64,
+    sweep_phase: f32,
+}
+
+impl Default for TestSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestSignal {
+    pub fn new() -> Self {
+        Self {
+            bpm: 120.0,
+            position: 0,
+            sweep_phase: 0.0,
+        }
+    }
+
+    /// The next `seconds` of the signal at [`TEST_SIGNAL_RATE`]
+    pub fn generate(&mut self, seconds: f32) -> Vec<f32> {
+        let count = (seconds.max(0.0) * TEST_SIGNAL_RATE) as usize;
+        let beat_length = 60.0 / self.bpm.max(1.0) as f64;
+        (0..count)
+            .map(|_| {
+                let time = self.position as f64 / TEST_SIGNAL_RATE as f64;
+                self.position += 1;
+                let since_beat = (time % beat_length) as f32;
+                let kick = (TAU * 55.0 * since_beat).sin() * (-since_beat * 20.0).exp();
+                // Sweep between 200 Hz and 4 kHz every 8 seconds
+                let sweep = 0.5 - 0.5 * (TAU * time as f32 / 8.0).cos();
+                let frequency = 200.0 * 20.0_f32.powf(sweep);
+                self.sweep_phase = (self.sweep_phase + TAU * frequency / TEST_SIGNAL_RATE) % TAU;
+                0.8 * kick + 0.1 * self.sweep_phase.sin()
+            })
+            .collect()
+    }
+}
+
+/// Where the analyzed audio comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioSource {
+    Off,
+    TestSignal,
+    /// An input device by name
+    Device(String),
+}
+
+impl AudioSource {
+    pub fn label(&self) -> &str {
+        match self {
+            AudioSource::Off => "Off",
+            AudioSource::TestSignal => "Test signal (120 BPM)",
+            AudioSource::Device(name) => name,
+        }
+    }
+}
+
+/// Names of the audio input devices, loopback sources included
+pub fn input_devices() -> Vec<String> {
+    #[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+    {
+        capture::input_devices()
+    }
+    #[cfg(not(all(feature = "audio", not(target_arch = "wasm32"))))]
+    {
+        Vec::new()
+    }
+}
+
+/// An audio source and the analysis of what it plays
+pub struct AudioInput {
+    source: AudioSource,
+    analyzer: AudioAnalyzer,
+    test_signal: TestSignal,
+    #[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+    capture: Option<capture::AudioCapture>,
+    /// Devices listed in the source picker
+    devices: Vec<String>,
+    error: Option<String>,
+    /// Applied to the samples before the analysis
+    pub gain: f32,
+}
+
+impl Default for AudioInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioInput {
+    pub fn new() -> Self {
+        Self {
+            source: AudioSource::Off,
+            analyzer: AudioAnalyzer::new(TEST_SIGNAL_RATE),
+            test_signal: TestSignal::new(),
+            #[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+            capture: None,
+            devices: Vec::new(),
+            error: None,
+            gain: 1.0,
+        }
+    }
+
+    pub fn source(&self) -> &AudioSource {
+        &self.source
+    }
+
+    /// Switch the source, starting capture for a device
+    ///
+    /// On failure the input is turned off.
+    pub fn set_source(&mut self, source: AudioSource) -> Result<(), AudioError> {
+        #[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+        {
+            self.capture = None;
+        }
+        self.source = AudioSource::Off;
+        self.analyzer.reset();
+        match &source {
+            AudioSource::Off => {}
+            AudioSource::TestSignal => {
+                self.test_signal = TestSignal::new();
+                self.analyzer.set_sample_rate(TEST_SIGNAL_RATE);
+            }
+            #[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+            AudioSource::Device(name) => {
+                let capture = capture::AudioCapture::start(name)?;
+                self.analyzer.set_sample_rate(capture.sample_rate());
+                self.capture = Some(capture);
+            }
+            #[cfg(not(all(feature = "audio", not(target_arch = "wasm32"))))]
+            AudioSource::Device(_) => return Err(AudioError::Unsupported),
+        }
+        self.source = source;
+        Ok(())
+    }
+
+    /// Take the audio of the last `dt` seconds and analyze it
+    pub fn update(&mut self, dt: f32) -> &AudioFeatures {
+        let mut samples = match &self.source {
+            AudioSource::Off => return self.analyzer.features(),
+            AudioSource::TestSignal => self.test_signal.generate(dt),
+            #[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+            AudioSource::Device(_) => self
+                .capture
+                .as_ref()
+                .map(|capture| capture.drain())
+                .unwrap_or_default(),
+            #[cfg(not(all(feature = "audio", not(target_arch = "wasm32"))))]
+            AudioSource::Device(_) => Vec::new(),
+        };
+        for sample in &mut samples {
+            *sample *= self.gain;
+        }
+        self.analyzer.push_samples(&samples);
+        self.analyzer.analyze(dt)
+    }
+
+    pub fn features(&self) -> &AudioFeatures {
+        self.analyzer.features()
+    }
+
+    /// Source picker, spectrum and meters
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.strong("🎵 Audio Input");
+        let mut selected = None;
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("audio_input_source")
+                .selected_text(self.source.label())
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    let sources = [AudioSource::Off, AudioSource::TestSignal]
+                        .into_iter()
+                        .chain(self.devices.iter().cloned().map(AudioSource::Device));
+                    for source in sources {
+                        let label = source.label().to_string();
+                        if ui.selectable_label(self.source == source, label).clicked() {
+                            selected = Some(source);
+                        }
+                    }
+                });
+            if ui
+                .small_button("⟳")
+                .on_hover_text("List the input devices again")
+                .clicked()
+            {
+                self.devices = input_devices();
+            }
+        });
+        if let Some(source) = selected {
+            self.error = self.set_source(source).err().map(|e| e.to_string());
+        }
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), error);
+        }
+        ui.add(egui::Slider::new(&mut self.gain, 0.0..=8.0).text("Gain"));
+
+        let features = *self.features();
+        let (rect, _) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 80.0), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+        let bar_width = rect.width() / BAND_COUNT as f32;
+        for (band, value) in features.bands.iter().enumerate() {
+            let left = rect.left() + band as f32 * bar_width;
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(left + 1.0, rect.bottom() - value * rect.height()),
+                egui::pos2(left + bar_width - 1.0, rect.bottom()),
+            );
+            let hue = band as f32 / BAND_COUNT as f32 * 0.7;
+            painter.rect_filled(bar, 0.0, egui::ecolor::Hsva::new(hue, 0.8, 0.9, 1.0));
+        }
+
+        for (name, value) in [
+            ("Level", features.level),
+            ("Bass", features.bass),
+            ("Mid", features.mid),
+            ("Treble", features.treble),
+        ] {
+            ui.add(egui::ProgressBar::new(value).text(name));
+        }
+        ui.horizontal(|ui| {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(14.0, 14.0), egui::Sense::hover());
+            let glow = (features.beat * 255.0) as u8;
+            ui.painter().circle_filled(
+                rect.center(),
+                6.0,
+                egui::Color32::from_rgb(glow, glow / 3, 40),
+            );
+            ui.label(format!("Beat ({} so far)", self.analyzer.beats()));
+        });
+        ui.label(
+            egui::RichText::new(
+                "In the shader: iAudio (level, bass, mid, treble), iBeat and audioBand(i) \
+                for the 16 bands",
+            )
+            .small()
+            .weak(),
+        );
+    }
+}
+
+/// Microphone and loopback capture with cpal
+#[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+mod capture {
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{FromSample, Sample, SizedSample};
+
+    use super::AudioError;
+
+    /// A running input stream and the samples it captured since the last
+    /// drain, a second at most
+    pub struct AudioCapture {
+        _stream: cpal::Stream,
+        samples: Arc<Mutex<VecDeque<f32>>>,
+        sample_rate: f32,
+    }
+
+    pub fn input_devices() -> Vec<String> {
+        cpal::default_host()
+            .input_devices()
+            .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    impl AudioCapture {
+        pub fn start(name: &str) -> Result<Self, AudioError> {
+            let device = cpal::default_host()
+                .input_devices()
+                .map_err(|e| AudioError::Device(e.to_string()))?
+                .find(|device| device.name().is_ok_and(|n| n == name))
+                .ok_or_else(|| AudioError::DeviceNotFound(name.to_string()))?;
+            let supported = device
+                .default_input_config()
+                .map_err(|e| AudioError::Device(e.to_string()))?;
+            let sample_rate = supported.sample_rate().0 as f32;
+            let config = supported.config();
+            let samples = Arc::new(Mutex::new(VecDeque::new()));
+            let stream = match supported.sample_format() {
+                cpal::SampleFormat::F32 => build::<f32>(&device, &config, samples.clone()),
+                cpal::SampleFormat::I16 => build::<i16>(&device, &config, samples.clone()),
+                cpal::SampleFormat::U16 => build::<u16>(&device, &config, samples.clone()),
+                format => {
+                    return Err(AudioError::Device(format!(
+                        "Unsupported sample format {:?}",
+                        format
+                    )))
+                }
+            }?;
+            stream
+                .play()
+                .map_err(|e| AudioError::Stream(e.to_string()))?;
+            Ok(Self {
+                _stream: stream,
+                samples,
+                sample_rate,
+            })
+        }
+
+        pub fn sample_rate(&self) -> f32 {
+            self.sample_rate
+        }
+
+        /// Take the samples captured since the last call
+        pub fn drain(&self) -> Vec<f32> {
+            self.samples
+                .lock()
+                .map(|mut samples| samples.drain(..).collect())
+                .unwrap_or_default()
+        }
+    }
+
+    fn build<T>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        samples: Arc<Mutex<VecDeque<f32>>>,
+    ) -> Result<cpal::Stream, AudioError>
+    where
+        T: SizedSample,
+        f32: FromSample<T>,
+    {
+        let channels = config.channels.max(1) as usize;
+        let capacity = config.sample_rate.0 as usize;
+        device
+            .build_input_stream(
+                config,
+                move |data: &[T], _: &cpal::InputCallbackInfo| {
+                    let Ok(mut samples) = samples.lock() else {
+                        return;
+                    };
+                    // Mix the channels down to mono
+                    for frame in data.chunks(channels) {
+                        let sum: f32 = frame.iter().map(|s| s.to_sample::<f32>()).sum();
+                        samples.push_back(sum / channels as f32);
+                    }
+                    let excess = samples.len().saturating_sub(capacity);
+                    samples.drain(..excess);
+                },
+                |e| log::warn!("Audio input error: {}", e),
+                None,
+            )
+            .map_err(|e| AudioError::Stream(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f32, amplitude: f32, count: usize) -> Vec<f32> {
+        (0..count)
+            .map(|i| amplitude * (TAU * frequency * i as f32 / TEST_SIGNAL_RATE).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_fft_finds_the_frequency() {
+        // 8 cycles over 64 samples land in bin 8 and its mirror
+        let mut re: Vec<f32> = (0..64)
+            .map(|i| (TAU * 8.0 * i as f32 / 64.0).cos())
+            .collect();
+        let mut im = vec![0.0; 64];
+        fft(&mut re, &mut im);
+        for (k, (re, im)) in re.iter().zip(&im).enumerate() {
+            let expected = if k == 8 || k == 56 { 32.0 } else { 0.0 };
+            assert!((re.hypot(*im) - expected).abs() < 1e-3, "bin {k}");
+        }
+    }
+
+    #[test]
+    fn test_band_edges() {
+        let edges = band_edges(TEST_SIGNAL_RATE);
+        assert_eq!(edges[0], LOWEST_FREQUENCY);
+        assert!((edges[BAND_COUNT] - HIGHEST_FREQUENCY).abs() < 1.0);
+        assert!(edges.windows(2).all(|pair| pair[0] < pair[1]));
+        // Low sample rates stop at the Nyquist frequency
+        assert!((band_edges(16_000.0)[BAND_COUNT] - 8_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_sine_lights_up_its_band() {
+        let mut analyzer = AudioAnalyzer::new(TEST_SIGNAL_RATE);
+        analyzer.push_samples(&sine(1000.0, 1.0, FFT_SIZE));
+        let features = *analyzer.analyze(1.0 / 60.0);
+        let edges = band_edges(TEST_SIGNAL_RATE);
+        let band = edges
+            .windows(2)
+            .position(|e| e[0] <= 1000.0 && 1000.0 < e[1]);
+        let loudest = (0..BAND_COUNT)
+            .max_by(|&a, &b| features.bands[a].total_cmp(&features.bands[b]))
+            .unwrap();
+        assert_eq!(Some(loudest), band);
+        assert!(features.bands[loudest] > 0.95);
+        assert!(features.bands[0] < 0.3);
+        assert!(features.level > 0.95);
+        assert!(features.mid > features.bass);
+    }
+
+    #[test]
+    fn test_silence_and_release() {
+        let mut analyzer = AudioAnalyzer::new(TEST_SIGNAL_RATE);
+        assert_eq!(*analyzer.analyze(1.0 / 60.0), AudioFeatures::default());
+
+        analyzer.push_samples(&sine(440.0, 0.5, FFT_SIZE));
+        let loud = analyzer.analyze(1.0 / 60.0).level;
+        analyzer.push_samples(&[0.0; FFT_SIZE]);
+        let falling = analyzer.analyze(1.0 / 60.0).level;
+        assert!(falling > 0.0 && falling < loud);
+        for _ in 0..120 {
+            analyzer.analyze(1.0 / 60.0);
+        }
+        assert!(analyzer.features().level < 0.01);
+    }
+
+    #[test]
+    fn test_beats_of_the_test_signal() {
+        let mut input = AudioInput::new();
+        input.set_source(AudioSource::TestSignal).unwrap();
+        let mut beat_frames = 0;
+        // Four seconds at 120 BPM
+        for _ in 0..240 {
+            let features = *input.update(1.0 / 60.0);
+            if features.beat == 1.0 {
+                beat_frames += 1;
+                // The kick drum is all bass
+                assert!(features.bass > 0.8 && features.treble < 0.5);
+            }
+        }
+        assert!((7..=9).contains(&beat_frames), "{beat_frames} beats");
+        assert_eq!(input.analyzer.beats(), beat_frames);
+
+        input.set_source(AudioSource::Off).unwrap();
+        assert_eq!(*input.update(1.0 / 60.0), AudioFeatures::default());
+    }
+
+    #[cfg(not(feature = "audio"))]
+    #[test]
+    fn test_devices_need_the_feature() {
+        let mut input = AudioInput::new();
+        assert_eq!(
+            input.set_source(AudioSource::Device("mic".to_string())),
+            Err(AudioError::Unsupported)
+        );
+        assert_eq!(input.source(), &AudioSource::Off);
+    }
+}
@@ -0,0 +1,188 @@
+//! Reference Manager - CLI for maintaining visual regression reference images
+//!
+//! A selective alternative to re-running all tests with
+//! `UPDATE_VISUAL_REFERENCES=1`.
+
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+use wgpu_playground_core::visual_regression::references::{ReferenceStatus, ReferenceStore};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 {
+        show_help();
+        return ExitCode::SUCCESS;
+    }
+
+    let store = ReferenceStore::default();
+
+    match args[1].as_str() {
+        "list" => cmd_list(&store, &args[2..]),
+        "promote" => cmd_promote(&store, &args[2..]),
+        "prune" => cmd_prune(&store, &args[2..]),
+        "validate" => cmd_validate(&store),
+        _ => {
+            show_help();
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+fn show_help() {
+    println!("Reference Manager v1.0");
+    println!();
+    println!("USAGE:");
+    println!("  reference_manager list [--stale]      Show the status of every reference");
+    println!("  reference_manager promote [names...]  Copy outputs over references");
+    println!("                                        (asks for each changed test if no names)");
+    println!("  reference_manager prune [--delete]    List (or delete) references without a test");
+    println!("  reference_manager validate            Fail if references and tests disagree");
+    println!();
+    println!("References used outside the tests (watch mode, server, FFI, Python) belong in");
+    println!(
+        "the manifest: {}",
+        ReferenceStore::default().manifest_path.display()
+    );
+}
+
+fn describe(status: &ReferenceStatus) -> String {
+    match status {
+        ReferenceStatus::Stale { difference } => {
+            format!("stale (Δ={:.4}%)", difference * 100.0)
+        }
+        ReferenceStatus::SizeChanged { reference, output } => format!(
+            "resized ({}x{} -> {}x{})",
+            reference.0, reference.1, output.0, output.1
+        ),
+        other => other.label().to_string(),
+    }
+}
+
+fn cmd_list(store: &ReferenceStore, params: &[String]) -> ExitCode {
+    let only_stale = params.iter().any(|p| p == "--stale");
+    let statuses = store.statuses();
+
+    if statuses.is_empty() {
+        println!("No references or visual tests found");
+        return ExitCode::SUCCESS;
+    }
+
+    for (name, status) in &statuses {
+        if only_stale && !status.is_promotable() {
+            continue;
+        }
+        println!("{:<32} {}", name, describe(status));
+    }
+    ExitCode::SUCCESS
+}
+
+fn cmd_promote(store: &ReferenceStore, params: &[String]) -> ExitCode {
+    if !params.is_empty() {
+        let mut failed = false;
+        for name in params {
+            match store.promote(name) {
+                Ok(path) => println!("Promoted {} -> {}", name, path.display()),
+                Err(why) => {
+                    eprintln!("Cannot promote {}: {}", name, why);
+                    failed = true;
+                }
+            }
+        }
+        return if failed {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        };
+    }
+
+    let candidates: Vec<(String, ReferenceStatus)> = store
+        .statuses()
+        .into_iter()
+        .filter(|(name, status)| status.is_promotable() && store.output_path(name).exists())
+        .collect();
+
+    if candidates.is_empty() {
+        println!("Nothing to promote");
+        return ExitCode::SUCCESS;
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    for (name, status) in candidates {
+        print!("Promote {} [{}]? [y/N/q] ", name, describe(&status));
+        io::stdout().flush().ok();
+
+        let answer = match lines.next() {
+            Some(Ok(line)) => line.trim().to_lowercase(),
+            _ => break,
+        };
+        match answer.as_str() {
+            "y" | "yes" => match store.promote(&name) {
+                Ok(path) => println!("  Promoted -> {}", path.display()),
+                Err(why) => eprintln!("  Failed: {}", why),
+            },
+            "q" | "quit" => break,
+            _ => println!("  Skipped"),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn cmd_prune(store: &ReferenceStore, params: &[String]) -> ExitCode {
+    let dry_run = !params.iter().any(|p| p == "--delete");
+    match store.prune(dry_run) {
+        Ok(paths) if paths.is_empty() => {
+            println!("No orphaned references");
+            ExitCode::SUCCESS
+        }
+        Ok(paths) => {
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            for path in paths {
+                println!("{} {}", verb, path.display());
+            }
+            if dry_run {
+                println!("Run with --delete to remove them");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(why) => {
+            eprintln!("Prune failed: {}", why);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cmd_validate(store: &ReferenceStore) -> ExitCode {
+    let problems: Vec<(String, ReferenceStatus)> = store
+        .statuses()
+        .into_iter()
+        .filter(|(_, status)| {
+            matches!(
+                status,
+                ReferenceStatus::Orphaned | ReferenceStatus::MissingReference
+            )
+        })
+        .collect();
+
+    if problems.is_empty() {
+        println!("RESULT: Every reference has a test and every test has a reference ✓");
+        return ExitCode::SUCCESS;
+    }
+
+    for (name, status) in &problems {
+        eprintln!("{:<32} {}", name, describe(status));
+    }
+    if problems
+        .iter()
+        .any(|(_, status)| *status == ReferenceStatus::Orphaned)
+    {
+        eprintln!(
+            "List references used outside the tests in {}",
+            store.manifest_path.display()
+        );
+    }
+    eprintln!("RESULT: {} problem(s) found ✗", problems.len());
+    ExitCode::FAILURE
+}
@@ -2,6 +2,8 @@ use crate::bind_group::{
     BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, SamplerBindingType,
     StorageTextureAccess, TextureSampleType, TextureViewDimension,
 };
+use crate::bind_group_panel::{BindingTypeConfig, STORAGE_TEXTURE_ACCESSES};
+use crate::state::{parse_variant, BindGroupEntryState};
 use crate::tooltip::{shader_visibility, TooltipExt};
 use wgpu::ShaderStages;
 
@@ -173,6 +175,78 @@ impl EntryConfig {
 
         Ok(BindGroupLayoutEntry::new(binding, visibility, binding_type))
     }
+
+    /// Serializable form of the entry, `None` if the binding number doesn't
+    /// parse
+    ///
+    /// Only the binding type, read-only flag and storage access are kept;
+    /// the other resource options export as the bind group panel's defaults.
+    fn to_state(&self) -> Option<BindGroupEntryState> {
+        let (binding_type, read_only, access) = match &self.resource_type {
+            ResourceTypeConfig::UniformBuffer { .. } => ("UniformBuffer", false, None),
+            ResourceTypeConfig::StorageBuffer { read_only, .. } => {
+                ("StorageBuffer", *read_only, None)
+            }
+            ResourceTypeConfig::Texture { .. } => ("Texture", false, None),
+            ResourceTypeConfig::Sampler { .. } => ("Sampler", false, None),
+            ResourceTypeConfig::StorageTexture { access, .. } => {
+                ("StorageTexture", false, Some(format!("{:?}", access)))
+            }
+        };
+        Some(BindGroupEntryState {
+            binding: self.binding_number.parse().ok()?,
+            visibility_vertex: self.visibility_vertex,
+            visibility_fragment: self.visibility_fragment,
+            visibility_compute: self.visibility_compute,
+            binding_type: binding_type.to_string(),
+            read_only,
+            access,
+        })
+    }
+
+    /// Entry saved with [`to_state`](Self::to_state), `None` if the binding
+    /// type is unknown
+    fn from_state(state: &BindGroupEntryState) -> Option<Self> {
+        let resource_type = match state.binding_type.as_str() {
+            "UniformBuffer" => ResourceTypeConfig::UniformBuffer {
+                has_dynamic_offset: false,
+                min_binding_size: String::new(),
+            },
+            "StorageBuffer" => ResourceTypeConfig::StorageBuffer {
+                has_dynamic_offset: false,
+                min_binding_size: String::new(),
+                read_only: state.read_only,
+            },
+            "Texture" => ResourceTypeConfig::Texture {
+                sample_type: TextureSampleTypeConfig::FloatFilterable,
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            "Sampler" => ResourceTypeConfig::Sampler {
+                sampler_type: SamplerBindingType::Filtering,
+            },
+            "StorageTexture" => {
+                let access = state
+                    .access
+                    .as_deref()
+                    .and_then(|name| parse_variant(name, &STORAGE_TEXTURE_ACCESSES))
+                    .unwrap_or(StorageTextureAccess::WriteOnly);
+                ResourceTypeConfig::StorageTexture {
+                    access,
+                    format: BindingTypeConfig::storage_texture_format(access),
+                    view_dimension: TextureViewDimension::D2,
+                }
+            }
+            _ => return None,
+        };
+        Some(Self {
+            binding_number: state.binding.to_string(),
+            visibility_vertex: state.visibility_vertex,
+            visibility_fragment: state.visibility_fragment,
+            visibility_compute: state.visibility_compute,
+            resource_type,
+        })
+    }
 }
 
 /// UI panel for creating and configuring bind group layouts
@@ -699,6 +773,32 @@ impl BindGroupLayoutPanel {
         }
     }
 
+    /// Export the current state to a serializable format
+    pub fn export_state(&self) -> crate::state::BindGroupLayoutPanelState {
+        crate::state::BindGroupLayoutPanelState {
+            label: self.label_input.clone(),
+            entries: self.entries.iter().filter_map(EntryConfig::to_state).collect(),
+        }
+    }
+
+    /// Import state from a serializable format
+    ///
+    /// Entries with unknown binding types are skipped; the panel keeps a
+    /// single default entry if none are left.
+    pub fn import_state(&mut self, state: &crate::state::BindGroupLayoutPanelState) {
+        self.label_input = state.label.clone();
+        self.entries = state
+            .entries
+            .iter()
+            .filter_map(EntryConfig::from_state)
+            .collect();
+        if self.entries.is_empty() {
+            self.entries.push(EntryConfig::default());
+        }
+        self.validation_error = None;
+        self.success_message = None;
+    }
+
     fn binding_type_display(binding_type: &BindingType) -> String {
         match binding_type {
             BindingType::UniformBuffer {
@@ -778,6 +878,36 @@ mod tests {
         assert_eq!(panel.entries.len(), 1);
     }
 
+    #[test]
+    fn test_state_roundtrip_keeps_entries() {
+        let mut panel = BindGroupLayoutPanel::new();
+        panel.label_input = "layout".to_string();
+        panel.add_entry();
+        panel.entries[1].binding_number = "3".to_string();
+        panel.entries[1].visibility_compute = true;
+        panel.entries[1].resource_type = ResourceTypeConfig::StorageBuffer {
+            has_dynamic_offset: false,
+            min_binding_size: String::new(),
+            read_only: true,
+        };
+
+        let state = panel.export_state();
+        assert_eq!(state.entries.len(), 2);
+        assert_eq!(state.entries[1].binding, 3);
+        assert_eq!(state.entries[1].binding_type, "StorageBuffer");
+
+        let mut restored = BindGroupLayoutPanel::new();
+        restored.import_state(&state);
+        assert_eq!(restored.label_input, "layout");
+        assert_eq!(restored.entries.len(), 2);
+        assert_eq!(restored.entries[1].binding_number, "3");
+        assert!(restored.entries[1].visibility_compute);
+        assert_eq!(
+            restored.entries[1].resource_type,
+            panel.entries[1].resource_type
+        );
+    }
+
     #[test]
     fn test_add_entry() {
         let mut panel = BindGroupLayoutPanel::new();
@@ -2,9 +2,21 @@ use crate::bind_group::{
     BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, SamplerBindingType,
     StorageTextureAccess, TextureSampleType, TextureViewDimension,
 };
-use crate::tooltip;
+use crate::device_recovery::ResourceRecreate;
+use crate::i18n::{tr, tr_args};
+use crate::operation_errors::OperationErrors;
+use crate::procedural_texture::BakedTexture;
+use crate::state::{parse_variant, BindGroupEntryState};
+use crate::tooltip::{self, TooltipExt};
 use wgpu::ShaderStages;
 
+/// Storage texture access modes, for parsing saved names
+pub(crate) const STORAGE_TEXTURE_ACCESSES: [StorageTextureAccess; 3] = [
+    StorageTextureAccess::WriteOnly,
+    StorageTextureAccess::ReadOnly,
+    StorageTextureAccess::ReadWrite,
+];
+
 /// UI panel for creating and configuring bind groups
 pub struct BindGroupPanel {
     /// Label input text for bind group layout
@@ -29,8 +41,13 @@ pub struct BindGroupPanel {
     success_message: Option<String>,
     /// Current UI mode
     ui_mode: UiMode,
+    /// Device errors from creating the layout
+    gpu_errors: OperationErrors,
 }
 
+/// Operation name of bind group layout creation in [`OperationErrors`]
+const CREATE_LAYOUT: &str = "create_layout";
+
 /// UI mode for the panel
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum UiMode {
@@ -50,6 +67,60 @@ pub struct BindGroupLayoutEntryConfig {
     pub binding_type: BindingTypeConfig,
 }
 
+impl BindGroupLayoutEntryConfig {
+    /// Serializable form of the entry
+    pub fn to_state(&self) -> BindGroupEntryState {
+        let (binding_type, read_only, access) = match &self.binding_type {
+            BindingTypeConfig::UniformBuffer => ("UniformBuffer", false, None),
+            BindingTypeConfig::StorageBuffer { read_only } => ("StorageBuffer", *read_only, None),
+            BindingTypeConfig::Texture => ("Texture", false, None),
+            BindingTypeConfig::Sampler => ("Sampler", false, None),
+            BindingTypeConfig::StorageTexture { access } => {
+                ("StorageTexture", false, Some(format!("{:?}", access)))
+            }
+        };
+        BindGroupEntryState {
+            binding: self.binding,
+            visibility_vertex: self.visibility.vertex,
+            visibility_fragment: self.visibility.fragment,
+            visibility_compute: self.visibility.compute,
+            binding_type: binding_type.to_string(),
+            read_only,
+            access,
+        }
+    }
+
+    /// Entry saved with [`to_state`](Self::to_state), `None` if the binding
+    /// type is unknown
+    pub fn from_state(state: &BindGroupEntryState) -> Option<Self> {
+        let binding_type = match state.binding_type.as_str() {
+            "UniformBuffer" => BindingTypeConfig::UniformBuffer,
+            "StorageBuffer" => BindingTypeConfig::StorageBuffer {
+                read_only: state.read_only,
+            },
+            "Texture" => BindingTypeConfig::Texture,
+            "Sampler" => BindingTypeConfig::Sampler,
+            "StorageTexture" => BindingTypeConfig::StorageTexture {
+                access: state
+                    .access
+                    .as_deref()
+                    .and_then(|name| parse_variant(name, &STORAGE_TEXTURE_ACCESSES))
+                    .unwrap_or(StorageTextureAccess::WriteOnly),
+            },
+            _ => return None,
+        };
+        Some(Self {
+            binding: state.binding,
+            visibility: ShaderStagesConfig {
+                vertex: state.visibility_vertex,
+                fragment: state.visibility_fragment,
+                compute: state.visibility_compute,
+            },
+            binding_type,
+        })
+    }
+}
+
 /// Shader stages configuration for UI
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ShaderStagesConfig {
@@ -80,6 +151,14 @@ impl ShaderStagesConfig {
             compute: false,
         }
     }
+
+    fn compute() -> Self {
+        Self {
+            vertex: false,
+            fragment: false,
+            compute: true,
+        }
+    }
 }
 
 /// Binding type configuration for UI
@@ -89,7 +168,7 @@ pub enum BindingTypeConfig {
     StorageBuffer { read_only: bool },
     Texture,
     Sampler,
-    StorageTexture,
+    StorageTexture { access: StorageTextureAccess },
 }
 
 impl BindingTypeConfig {
@@ -112,22 +191,47 @@ impl BindingTypeConfig {
             BindingTypeConfig::Sampler => BindingType::Sampler {
                 sampler_type: SamplerBindingType::Filtering,
             },
-            BindingTypeConfig::StorageTexture => BindingType::StorageTexture {
-                access: StorageTextureAccess::WriteOnly,
-                format: wgpu::TextureFormat::Rgba8Unorm,
+            BindingTypeConfig::StorageTexture { access } => BindingType::StorageTexture {
+                access: *access,
+                format: Self::storage_texture_format(*access),
                 view_dimension: TextureViewDimension::D2,
             },
         }
     }
 
-    pub fn name(&self) -> &'static str {
+    /// Format of a storage texture binding with `access`
+    ///
+    /// Read-write access is only guaranteed for the single-channel 32-bit
+    /// formats, the other modes use a common color format.
+    pub fn storage_texture_format(access: StorageTextureAccess) -> wgpu::TextureFormat {
+        match access {
+            StorageTextureAccess::ReadWrite => wgpu::TextureFormat::R32Float,
+            StorageTextureAccess::WriteOnly | StorageTextureAccess::ReadOnly => {
+                wgpu::TextureFormat::Rgba8Unorm
+            }
+        }
+    }
+
+    pub fn name(&self) -> String {
         match self {
-            BindingTypeConfig::UniformBuffer => "Uniform Buffer",
-            BindingTypeConfig::StorageBuffer { read_only: true } => "Storage Buffer (Read-Only)",
-            BindingTypeConfig::StorageBuffer { read_only: false } => "Storage Buffer (Read-Write)",
-            BindingTypeConfig::Texture => "Texture",
-            BindingTypeConfig::Sampler => "Sampler",
-            BindingTypeConfig::StorageTexture => "Storage Texture",
+            BindingTypeConfig::UniformBuffer => tr("bind-group-type-uniform-buffer"),
+            BindingTypeConfig::StorageBuffer { read_only: true } => {
+                tr("bind-group-type-storage-buffer-read-only")
+            }
+            BindingTypeConfig::StorageBuffer { read_only: false } => {
+                tr("bind-group-type-storage-buffer-read-write")
+            }
+            BindingTypeConfig::Texture => tr("bind-group-type-texture"),
+            BindingTypeConfig::Sampler => tr("bind-group-type-sampler"),
+            BindingTypeConfig::StorageTexture {
+                access: StorageTextureAccess::WriteOnly,
+            } => tr("bind-group-type-storage-texture-write-only"),
+            BindingTypeConfig::StorageTexture {
+                access: StorageTextureAccess::ReadOnly,
+            } => tr("bind-group-type-storage-texture-read-only"),
+            BindingTypeConfig::StorageTexture {
+                access: StorageTextureAccess::ReadWrite,
+            } => tr("bind-group-type-storage-texture-read-write"),
         }
     }
 
@@ -138,7 +242,9 @@ impl BindingTypeConfig {
             BindingTypeConfig::StorageBuffer { read_only: false },
             BindingTypeConfig::Texture,
             BindingTypeConfig::Sampler,
-            BindingTypeConfig::StorageTexture,
+            BindingTypeConfig::StorageTexture {
+                access: StorageTextureAccess::WriteOnly,
+            },
         ]
     }
 }
@@ -157,6 +263,8 @@ struct MockTexture {
     name: String,
     format: String,
     dimensions: String,
+    /// Baked in the Textures tab rather than a demonstration texture
+    baked: bool,
 }
 
 /// Mock sampler for UI demonstration
@@ -207,11 +315,19 @@ impl BindGroupPanel {
                 name: "Color Texture".to_string(),
                 format: "Rgba8Unorm".to_string(),
                 dimensions: "256x256".to_string(),
+                baked: false,
             },
             MockTexture {
                 name: "Depth Texture".to_string(),
                 format: "Depth32Float".to_string(),
                 dimensions: "512x512".to_string(),
+                baked: false,
+            },
+            MockTexture {
+                name: "Storage Texture".to_string(),
+                format: "R32Float".to_string(),
+                dimensions: "256x256".to_string(),
+                baked: false,
             },
         ];
 
@@ -239,14 +355,51 @@ impl BindGroupPanel {
             validation_error: None,
             success_message: None,
             ui_mode: UiMode::CreateLayout,
+            gpu_errors: OperationErrors::new(),
+        }
+    }
+
+    /// Offer the texture baked in the Textures tab for binding, in place of
+    /// the one offered before; `None` withdraws it and its assignments
+    pub fn set_baked_texture(&mut self, baked: Option<&BakedTexture>) {
+        let texture = baked.map(|baked| {
+            let (width, height) = baked.size();
+            MockTexture {
+                name: format!("🎲 {}", baked.label),
+                format: format!("{:?}", baked.format()),
+                dimensions: format!("{}x{}", width, height),
+                baked: true,
+            }
+        });
+        self.offer_baked_texture(texture);
+    }
+
+    fn offer_baked_texture(&mut self, texture: Option<MockTexture>) {
+        let index = self.mock_textures.iter().position(|texture| texture.baked);
+        match (index, texture) {
+            (Some(index), Some(texture)) => self.mock_textures[index] = texture,
+            (None, Some(texture)) => self.mock_textures.push(texture),
+            (Some(index), None) => {
+                // The baked texture is last, so other indices stay valid
+                self.mock_textures.remove(index);
+                self.binding_assignments.retain(|(_, assignment)| {
+                    !matches!(assignment, ResourceAssignment::Texture(i) if *i == index)
+                });
+            }
+            (None, None) => {}
         }
     }
 
     /// Add a new binding entry
     fn add_binding_entry(&mut self, binding_type: BindingTypeConfig) {
+        // Storage textures are mostly written by compute shaders
+        let visibility = match binding_type {
+            BindingTypeConfig::StorageTexture { .. } => ShaderStagesConfig::compute(),
+            _ => ShaderStagesConfig::all(),
+        };
         let entry = BindGroupLayoutEntryConfig {
             binding: self.next_binding,
-            visibility: ShaderStagesConfig::all(),
+            visibility,
             binding_type,
         };
         self.layout_entries.push(entry);
@@ -271,8 +424,7 @@ impl BindGroupPanel {
     /// Validate the bind group layout
     fn validate_layout(&mut self) -> bool {
         if self.layout_entries.is_empty() {
-            self.validation_error =
-                Some("Bind group layout must have at least one entry".to_string());
+            self.validation_error = Some(tr("bind-group-layout-empty"));
             self.success_message = None;
             return false;
         }
@@ -281,25 +433,72 @@ impl BindGroupPanel {
         for entry in &self.layout_entries {
             let stages = entry.visibility.to_wgpu();
             if stages.is_empty() {
-                self.validation_error = Some(format!(
-                    "Binding {} must be visible in at least one shader stage",
-                    entry.binding
+                self.validation_error = Some(tr_args(
+                    "bind-group-binding-invisible",
+                    &[("binding", &entry.binding)],
                 ));
                 self.success_message = None;
                 return false;
             }
+
+            // Vertex shaders can't write to storage textures
+            if let BindingTypeConfig::StorageTexture { access } = entry.binding_type {
+                if entry.visibility.vertex && access != StorageTextureAccess::ReadOnly {
+                    self.validation_error = Some(tr_args(
+                        "bind-group-binding-vertex-storage",
+                        &[("binding", &entry.binding)],
+                    ));
+                    self.success_message = None;
+                    return false;
+                }
+            }
         }
 
         self.validation_error = None;
-        self.success_message = Some("✓ Layout configuration is valid".to_string());
+        self.success_message = Some(tr("bind-group-layout-valid"));
         true
     }
 
+    /// Create the bind group layout on the device
+    ///
+    /// Errors the device reports are shown below the Create Layout button.
+    pub fn create_layout(&mut self, device: &wgpu::Device) -> Option<wgpu::BindGroupLayout> {
+        if !self.validate_layout() {
+            return None;
+        }
+        let descriptor = self.get_layout_descriptor()?;
+
+        let result = self
+            .gpu_errors
+            .scoped(device, CREATE_LAYOUT, || descriptor.create_layout(device));
+        match result {
+            // The device rejected the layout; the error is shown inline
+            Ok(_) if self.gpu_errors.has_error(CREATE_LAYOUT) => {
+                self.success_message = None;
+                None
+            }
+            Ok(layout) => {
+                let label = descriptor
+                    .label()
+                    .map_or_else(|| tr("bind-group-unnamed"), str::to_string);
+                self.success_message =
+                    Some(tr_args("bind-group-layout-created", &[("label", &label)]));
+                self.validation_error = None;
+                Some(layout)
+            }
+            Err(e) => {
+                self.validation_error =
+                    Some(tr_args("bind-group-layout-create-failed", &[("error", &e)]));
+                self.success_message = None;
+                None
+            }
+        }
+    }
+
     /// Validate the bind group bindings
     fn validate_bindings(&mut self) -> bool {
         if self.layout_entries.is_empty() {
-            self.validation_error =
-                Some("Create a layout first before binding resources".to_string());
+            self.validation_error = Some(tr("bind-group-needs-layout"));
             self.success_message = None;
             return false;
         }
@@ -311,9 +510,9 @@ impl BindGroupPanel {
                 .iter()
                 .any(|(b, _)| *b == entry.binding);
             if !has_assignment {
-                self.validation_error = Some(format!(
-                    "Binding {} has no resource assigned",
-                    entry.binding
+                self.validation_error = Some(tr_args(
+                    "bind-group-binding-unassigned",
+                    &[("binding", &entry.binding)],
                 ));
                 self.success_message = None;
                 return false;
@@ -321,32 +520,46 @@ impl BindGroupPanel {
         }
 
         self.validation_error = None;
-        self.success_message = Some("✓ All bindings are assigned".to_string());
+        self.success_message = Some(tr("bind-group-bindings-valid"));
         true
     }
 
     /// Render the bind group configuration UI
     pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.ui_with_device(ui, None);
+    }
+
+    /// Render the bind group configuration UI, creating the layout on
+    /// `device` when one is available
+    pub fn ui_with_device(&mut self, ui: &mut egui::Ui, device: Option<&wgpu::Device>) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("🔗 Bind Group Configuration");
-            ui.label("Create bind group layouts and assign resources to binding slots.");
+            ui.heading(tr("bind-group-heading"));
+            ui.label(tr("bind-group-intro"));
             ui.add_space(10.0);
 
             // Mode selector
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut self.ui_mode, UiMode::CreateLayout, "1️⃣ Create Layout");
+                ui.selectable_value(
+                    &mut self.ui_mode,
+                    UiMode::CreateLayout,
+                    tr("bind-group-mode-layout"),
+                );
                 ui.selectable_value(
                     &mut self.ui_mode,
                     UiMode::BindResources,
-                    "2️⃣ Bind Resources",
+                    tr("bind-group-mode-resources"),
+                );
+                ui.selectable_value(
+                    &mut self.ui_mode,
+                    UiMode::Visualization,
+                    tr("bind-group-mode-visualization"),
                 );
-                ui.selectable_value(&mut self.ui_mode, UiMode::Visualization, "3️⃣ Visualization");
             });
 
             ui.add_space(10.0);
 
             match self.ui_mode {
-                UiMode::CreateLayout => self.render_layout_ui(ui),
+                UiMode::CreateLayout => self.render_layout_ui(ui, device),
                 UiMode::BindResources => self.render_binding_ui(ui),
                 UiMode::Visualization => self.render_visualization_ui(ui),
             }
@@ -355,7 +568,10 @@ impl BindGroupPanel {
 
             // Display validation errors or success messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("bind-group-error", &[("error", error)]),
+                );
             }
 
             if let Some(success) = &self.success_message {
@@ -365,14 +581,14 @@ impl BindGroupPanel {
     }
 
     /// Render the layout creation UI
-    fn render_layout_ui(&mut self, ui: &mut egui::Ui) {
+    fn render_layout_ui(&mut self, ui: &mut egui::Ui, device: Option<&wgpu::Device>) {
         // Layout properties
         ui.group(|ui| {
-            ui.heading("Bind Group Layout");
+            ui.heading(tr("bind-group-layout-heading"));
             ui.add_space(5.0);
 
             ui.horizontal(|ui| {
-                ui.label("Label:");
+                ui.label(tr("bind-group-label"));
                 ui.text_edit_singleline(&mut self.layout_label_input);
             });
         });
@@ -381,12 +597,12 @@ impl BindGroupPanel {
 
         // Binding entries
         ui.group(|ui| {
-            ui.heading("Binding Entries");
-            ui.label("Define the layout slots for resources:");
+            ui.heading(tr("bind-group-entries-heading"));
+            ui.label(tr("bind-group-entries-intro"));
             ui.add_space(5.0);
 
             if self.layout_entries.is_empty() {
-                ui.label("No binding entries yet. Add one below.");
+                ui.label(tr("bind-group-entries-empty"));
             } else {
                 // Display current entries in a table
                 egui::Grid::new("layout_entries_grid")
@@ -395,11 +611,11 @@ impl BindGroupPanel {
                     .striped(true)
                     .show(ui, |ui| {
                         // Header
-                        ui.label("Binding");
-                        ui.label("Type");
-                        ui.label("Vertex");
-                        ui.label("Fragment");
-                        ui.label("Compute");
+                        ui.label(tr("bind-group-column-binding"));
+                        ui.label(tr("bind-group-column-type"));
+                        ui.label(tr("bind-group-column-vertex"));
+                        ui.label(tr("bind-group-column-fragment"));
+                        ui.label(tr("bind-group-column-compute"));
                         ui.label(""); // Actions column
                         ui.end_row();
 
@@ -407,7 +623,14 @@ impl BindGroupPanel {
                         let mut to_remove = None;
                         for (idx, entry) in self.layout_entries.iter_mut().enumerate() {
                             ui.label(format!("{}", entry.binding));
-                            ui.label(entry.binding_type.name());
+                            let name = entry.binding_type.name();
+                            if let BindingTypeConfig::StorageTexture { access } =
+                                &mut entry.binding_type
+                            {
+                                storage_access_selector(ui, idx, access, &name);
+                            } else {
+                                ui.label(name);
+                            }
 
                             tooltip::shader_visibility::VERTEX
                                 .apply(ui.checkbox(&mut entry.visibility.vertex, ""));
@@ -431,7 +654,7 @@ impl BindGroupPanel {
             ui.add_space(10.0);
 
             // Add new binding
-            ui.label("Add new binding:");
+            ui.label(tr("bind-group-add-binding"));
             ui.horizontal_wrapped(|ui| {
                 for binding_type in BindingTypeConfig::all() {
                     if ui.button(binding_type.name()).clicked() {
@@ -445,11 +668,25 @@ impl BindGroupPanel {
 
         // Actions
         ui.horizontal(|ui| {
-            if ui.button("🔍 Validate Layout").clicked() {
+            if ui.button(tr("bind-group-validate-layout")).clicked() {
                 self.validate_layout();
             }
 
-            if ui.button("🔄 Reset").clicked() {
+            if ui
+                .add_enabled(
+                    device.is_some(),
+                    egui::Button::new(tr("bind-group-create-layout")),
+                )
+                .on_hover_text(tr("bind-group-create-layout-hover"))
+                .on_disabled_hover_text(tr("bind-group-create-layout-needs-device"))
+                .clicked()
+            {
+                if let Some(device) = device {
+                    self.create_layout(device);
+                }
+            }
+
+            if ui.button(tr("bind-group-reset")).clicked() {
                 self.layout_entries.clear();
                 self.binding_assignments.clear();
                 self.next_binding = 0;
@@ -457,8 +694,10 @@ impl BindGroupPanel {
                 self.bind_group_label_input.clear();
                 self.validation_error = None;
                 self.success_message = None;
+                self.gpu_errors.clear_all();
             }
         });
+        self.gpu_errors.ui(ui, CREATE_LAYOUT);
     }
 
     /// Render the resource binding UI
@@ -466,18 +705,18 @@ impl BindGroupPanel {
         if self.layout_entries.is_empty() {
             ui.colored_label(
                 egui::Color32::from_rgb(200, 150, 50),
-                "⚠ Create a bind group layout first in the 'Create Layout' tab",
+                tr("bind-group-layout-missing"),
             );
             return;
         }
 
         // Bind group properties
         ui.group(|ui| {
-            ui.heading("Bind Group");
+            ui.heading(tr("bind-group-group-heading"));
             ui.add_space(5.0);
 
             ui.horizontal(|ui| {
-                ui.label("Label:");
+                ui.label(tr("bind-group-label"));
                 ui.text_edit_singleline(&mut self.bind_group_label_input);
             });
         });
@@ -486,24 +725,25 @@ impl BindGroupPanel {
 
         // Display layout summary
         ui.group(|ui| {
-            ui.heading("Layout Summary");
-            ui.label(format!(
-                "Layout: {}",
-                if self.layout_label_input.is_empty() {
-                    "<unnamed>"
-                } else {
-                    &self.layout_label_input
-                }
+            ui.heading(tr("bind-group-summary-heading"));
+            let label = if self.layout_label_input.is_empty() {
+                tr("bind-group-unnamed")
+            } else {
+                self.layout_label_input.clone()
+            };
+            ui.label(tr_args("bind-group-summary-layout", &[("label", &label)]));
+            ui.label(tr_args(
+                "bind-group-summary-bindings",
+                &[("count", &self.layout_entries.len())],
             ));
-            ui.label(format!("Bindings: {}", self.layout_entries.len()));
         });
 
         ui.add_space(10.0);
 
         // Binding assignments
         ui.group(|ui| {
-            ui.heading("Resource Assignments");
-            ui.label("Assign resources to each binding slot:");
+            ui.heading(tr("bind-group-assignments-heading"));
+            ui.label(tr("bind-group-assignments-intro"));
             ui.add_space(5.0);
 
             // Clone entries data to avoid borrow checker issues
@@ -516,7 +756,10 @@ impl BindGroupPanel {
             for (binding, binding_type) in entries {
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.label(format!("Binding {}:", binding));
+                        ui.label(tr_args(
+                            "bind-group-assignment-binding",
+                            &[("binding", &binding)],
+                        ));
                         ui.label(format!("({})", binding_type.name()));
                     });
 
@@ -531,23 +774,29 @@ impl BindGroupPanel {
                     match current_assignment {
                         Some(ResourceAssignment::Buffer(idx)) => {
                             if let Some(buffer) = self.mock_buffers.get(*idx) {
-                                ui.label(format!("Assigned: {}", buffer.name));
+                                ui.label(tr_args("bind-group-assigned", &[("name", &buffer.name)]));
                             }
                         }
                         Some(ResourceAssignment::Texture(idx)) => {
                             if let Some(texture) = self.mock_textures.get(*idx) {
-                                ui.label(format!("Assigned: {}", texture.name));
+                                ui.label(tr_args(
+                                    "bind-group-assigned",
+                                    &[("name", &texture.name)],
+                                ));
                             }
                         }
                         Some(ResourceAssignment::Sampler(idx)) => {
                             if let Some(sampler) = self.mock_samplers.get(*idx) {
-                                ui.label(format!("Assigned: {}", sampler.name));
+                                ui.label(tr_args(
+                                    "bind-group-assigned",
+                                    &[("name", &sampler.name)],
+                                ));
                             }
                         }
                         None => {
                             ui.colored_label(
                                 egui::Color32::from_rgb(200, 150, 50),
-                                "⚠ Not assigned",
+                                tr("bind-group-not-assigned"),
                             );
                         }
                     }
@@ -558,7 +807,7 @@ impl BindGroupPanel {
                         | BindingTypeConfig::StorageBuffer { .. } => {
                             self.render_buffer_selector(ui, binding);
                         }
-                        BindingTypeConfig::Texture | BindingTypeConfig::StorageTexture => {
+                        BindingTypeConfig::Texture | BindingTypeConfig::StorageTexture { .. } => {
                             self.render_texture_selector(ui, binding);
                         }
                         BindingTypeConfig::Sampler => {
@@ -574,22 +823,19 @@ impl BindGroupPanel {
 
         // Actions
         ui.horizontal(|ui| {
-            if ui.button("🔍 Validate Bindings").clicked() {
+            if ui.button(tr("bind-group-validate-bindings")).clicked() {
                 self.validate_bindings();
             }
 
-            if ui.button("✨ Create Bind Group").clicked() && self.validate_bindings() {
-                self.success_message = Some(
-                    "✓ Configuration is valid. In a full implementation, the bind group would be created here."
-                        .to_string(),
-                );
+            if ui.button(tr("bind-group-create")).clicked() && self.validate_bindings() {
+                self.success_message = Some(tr("bind-group-valid"));
             }
         });
     }
 
     /// Render buffer selector for a binding
     fn render_buffer_selector(&mut self, ui: &mut egui::Ui, binding: u32) {
-        ui.label("Available Buffers:");
+        ui.label(tr("bind-group-available-buffers"));
         for (idx, buffer) in self.mock_buffers.iter().enumerate() {
             if ui.button(&buffer.name).clicked() {
                 // Remove any existing assignment for this binding
@@ -600,13 +846,19 @@ impl BindGroupPanel {
                 self.validation_error = None;
                 self.success_message = None;
             }
-            ui.label(format!("  {} bytes, {}", buffer.size, buffer.usage));
+            ui.label(format!(
+                "  {}",
+                tr_args(
+                    "bind-group-buffer-details",
+                    &[("size", &buffer.size), ("usage", &buffer.usage)],
+                )
+            ));
         }
     }
 
     /// Render texture selector for a binding
     fn render_texture_selector(&mut self, ui: &mut egui::Ui, binding: u32) {
-        ui.label("Available Textures:");
+        ui.label(tr("bind-group-available-textures"));
         for (idx, texture) in self.mock_textures.iter().enumerate() {
             if ui.button(&texture.name).clicked() {
                 // Remove any existing assignment for this binding
@@ -623,7 +875,7 @@ impl BindGroupPanel {
 
     /// Render sampler selector for a binding
     fn render_sampler_selector(&mut self, ui: &mut egui::Ui, binding: u32) {
-        ui.label("Available Samplers:");
+        ui.label(tr("bind-group-available-samplers"));
         for (idx, sampler) in self.mock_samplers.iter().enumerate() {
             if ui.button(&sampler.name).clicked() {
                 // Remove any existing assignment for this binding
@@ -634,7 +886,13 @@ impl BindGroupPanel {
                 self.validation_error = None;
                 self.success_message = None;
             }
-            ui.label(format!("  Filter: {}", sampler.filter_mode));
+            ui.label(format!(
+                "  {}",
+                tr_args(
+                    "bind-group-sampler-details",
+                    &[("filter", &sampler.filter_mode)]
+                )
+            ));
         }
     }
 
@@ -645,13 +903,13 @@ impl BindGroupPanel {
         if self.layout_entries.is_empty() {
             ui.colored_label(
                 egui::Color32::from_rgb(200, 150, 50),
-                "⚠ Create a bind group layout first in the 'Create Layout' tab",
+                tr("bind-group-layout-missing"),
             );
             return;
         }
 
-        ui.heading("Bind Group Flow Diagram");
-        ui.label("Visual representation of how resources flow through the rendering pipeline.");
+        ui.heading(tr("bind-group-diagram-heading"));
+        ui.label(tr("bind-group-diagram-intro"));
         ui.add_space(10.0);
 
         // Prepare binding assignments for visualization
@@ -664,17 +922,17 @@ impl BindGroupPanel {
                         .mock_buffers
                         .get(*idx)
                         .map(|b| b.name.clone())
-                        .unwrap_or_else(|| "Unknown Buffer".to_string()),
+                        .unwrap_or_else(|| tr("bind-group-unknown-buffer")),
                     ResourceAssignment::Texture(idx) => self
                         .mock_textures
                         .get(*idx)
                         .map(|t| t.name.clone())
-                        .unwrap_or_else(|| "Unknown Texture".to_string()),
+                        .unwrap_or_else(|| tr("bind-group-unknown-texture")),
                     ResourceAssignment::Sampler(idx) => self
                         .mock_samplers
                         .get(*idx)
                         .map(|s| s.name.clone())
-                        .unwrap_or_else(|| "Unknown Sampler".to_string()),
+                        .unwrap_or_else(|| tr("bind-group-unknown-sampler")),
                 };
                 (*binding, resource_name)
             })
@@ -693,14 +951,12 @@ impl BindGroupPanel {
 
         // Add description
         ui.group(|ui| {
-            ui.heading("How to read this diagram:");
-            ui.label("• Pipeline Stages (left): Shader stages where bindings are accessible");
-            ui.label("• Bindings (center): Configured binding slots with their types");
-            ui.label("• Resources (right): Actual GPU resources bound to each slot");
-            ui.label(
-                "• Arrows: Show the data flow from resources through bindings to pipeline stages",
-            );
-            ui.label("• Colors: Different binding types are color-coded (see legend)");
+            ui.heading(tr("bind-group-diagram-help"));
+            ui.label(tr("bind-group-diagram-stages"));
+            ui.label(tr("bind-group-diagram-bindings"));
+            ui.label(tr("bind-group-diagram-resources"));
+            ui.label(tr("bind-group-diagram-arrows"));
+            ui.label(tr("bind-group-diagram-colors"));
         });
     }
 
@@ -727,6 +983,81 @@ impl BindGroupPanel {
 
         Some(descriptor)
     }
+
+    /// Export the current state to a serializable format
+    pub fn export_state(&self) -> crate::state::BindGroupPanelState {
+        crate::state::BindGroupPanelState {
+            label: self.bind_group_label_input.clone(),
+            entries: self
+                .layout_entries
+                .iter()
+                .map(BindGroupLayoutEntryConfig::to_state)
+                .collect(),
+        }
+    }
+
+    /// Import state from a serializable format
+    ///
+    /// Entries with unknown binding types are skipped. Resource assignments
+    /// refer to the panel's example resources, so they start out empty.
+    pub fn import_state(&mut self, state: &crate::state::BindGroupPanelState) {
+        self.bind_group_label_input = state.label.clone();
+        self.layout_entries = state
+            .entries
+            .iter()
+            .filter_map(BindGroupLayoutEntryConfig::from_state)
+            .collect();
+        self.next_binding = self
+            .layout_entries
+            .iter()
+            .map(|entry| entry.binding + 1)
+            .max()
+            .unwrap_or(0);
+        self.selected_binding = None;
+        self.binding_assignments.clear();
+        self.validation_error = None;
+        self.success_message = None;
+    }
+}
+
+/// Combo box picking the access mode of a storage texture entry
+fn storage_access_selector(
+    ui: &mut egui::Ui,
+    index: usize,
+    access: &mut StorageTextureAccess,
+    selected: &str,
+) {
+    egui::ComboBox::from_id_salt(format!("storage_access_{}", index))
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(
+                access,
+                StorageTextureAccess::WriteOnly,
+                tr("bind-group-access-write-only"),
+            );
+            ui.selectable_value(
+                access,
+                StorageTextureAccess::ReadOnly,
+                tr("bind-group-access-read-only"),
+            );
+            ui.selectable_value(
+                access,
+                StorageTextureAccess::ReadWrite,
+                tr("bind-group-access-read-write"),
+            );
+        })
+        .response
+        .webgpu_tooltip(
+            &tr("bind-group-access-tooltip"),
+            Some("#dom-gpustoragetexturebindinglayout-access"),
+        );
+}
+
+impl ResourceRecreate for BindGroupPanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // Layouts are created on demand; only errors from the old device remain
+        self.gpu_errors.clear_all();
+    }
 }
 
 #[cfg(test)]
@@ -805,6 +1136,91 @@ mod tests {
         );
         assert_eq!(BindingTypeConfig::Texture.name(), "Texture");
         assert_eq!(BindingTypeConfig::Sampler.name(), "Sampler");
+        assert_eq!(
+            BindingTypeConfig::StorageTexture {
+                access: StorageTextureAccess::ReadWrite
+            }
+            .name(),
+            "Storage Texture (Read-Write)"
+        );
+    }
+
+    #[test]
+    fn test_storage_texture_access_selects_format() {
+        for (access, format) in [
+            (
+                StorageTextureAccess::WriteOnly,
+                wgpu::TextureFormat::Rgba8Unorm,
+            ),
+            (
+                StorageTextureAccess::ReadOnly,
+                wgpu::TextureFormat::Rgba8Unorm,
+            ),
+            (
+                StorageTextureAccess::ReadWrite,
+                wgpu::TextureFormat::R32Float,
+            ),
+        ] {
+            let binding_type = BindingTypeConfig::StorageTexture { access }.to_binding_type();
+            assert_eq!(
+                binding_type,
+                BindingType::StorageTexture {
+                    access,
+                    format,
+                    view_dimension: TextureViewDimension::D2,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_writable_storage_texture_rejected_in_vertex_stage() {
+        let mut panel = BindGroupPanel::new();
+        panel.add_binding_entry(BindingTypeConfig::StorageTexture {
+            access: StorageTextureAccess::ReadWrite,
+        });
+        assert!(panel.layout_entries[0].visibility.compute);
+        assert!(panel.validate_layout());
+
+        panel.layout_entries[0].visibility.vertex = true;
+        assert!(!panel.validate_layout());
+
+        panel.layout_entries[0].binding_type = BindingTypeConfig::StorageTexture {
+            access: StorageTextureAccess::ReadOnly,
+        };
+        assert!(panel.validate_layout());
+    }
+
+    #[test]
+    fn test_state_roundtrip_keeps_entries() {
+        let mut panel = BindGroupPanel::new();
+        panel.bind_group_label_input = "material".to_string();
+        panel.add_binding_entry(BindingTypeConfig::UniformBuffer);
+        panel.add_binding_entry(BindingTypeConfig::StorageBuffer { read_only: true });
+        panel.add_binding_entry(BindingTypeConfig::StorageTexture {
+            access: StorageTextureAccess::ReadWrite,
+        });
+
+        let state = panel.export_state();
+        assert_eq!(state.entries.len(), 3);
+        assert_eq!(state.entries[2].access.as_deref(), Some("ReadWrite"));
+
+        let mut restored = BindGroupPanel::new();
+        restored.import_state(&state);
+        assert_eq!(restored.bind_group_label_input, "material");
+        assert_eq!(restored.layout_entries.len(), 3);
+        assert_eq!(
+            restored.layout_entries[1].binding_type,
+            BindingTypeConfig::StorageBuffer { read_only: true }
+        );
+        assert_eq!(
+            restored.layout_entries[2].binding_type,
+            BindingTypeConfig::StorageTexture {
+                access: StorageTextureAccess::ReadWrite
+            }
+        );
+        assert!(restored.layout_entries[2].visibility.compute);
+        assert_eq!(restored.next_binding, 3);
     }
 
     #[test]
@@ -847,6 +1263,31 @@ mod tests {
         assert!(panel.validate_bindings());
     }
 
+    #[test]
+    fn test_baked_texture_offered_for_binding() {
+        let mut panel = BindGroupPanel::new();
+        let mock_count = panel.mock_textures.len();
+        let baked = |name: &str| MockTexture {
+            name: name.to_string(),
+            format: "Rgba8UnormSrgb".to_string(),
+            dimensions: "64x64".to_string(),
+            baked: true,
+        };
+
+        panel.offer_baked_texture(Some(baked("🎲 Bricks")));
+        panel.offer_baked_texture(Some(baked("🎲 Perlin Noise")));
+        assert_eq!(panel.mock_textures.len(), mock_count + 1);
+        assert_eq!(panel.mock_textures[mock_count].name, "🎲 Perlin Noise");
+
+        panel.add_binding_entry(BindingTypeConfig::Texture);
+        panel
+            .binding_assignments
+            .push((0, ResourceAssignment::Texture(mock_count)));
+        panel.offer_baked_texture(None);
+        assert_eq!(panel.mock_textures.len(), mock_count);
+        assert!(panel.binding_assignments.is_empty());
+    }
+
     #[test]
     fn test_ui_mode_switching() {
         let mut panel = BindGroupPanel::new();
@@ -2,6 +2,7 @@
 ///
 /// Visual diagram showing bind group layouts and their connections to resources
 /// and pipeline stages. Helps users understand resource flow through the pipeline.
+use crate::bind_group::StorageTextureAccess;
 use crate::bind_group_panel::{BindGroupLayoutEntryConfig, BindingTypeConfig, ShaderStagesConfig};
 use egui::{Color32, Pos2, Rect, Stroke, Vec2};
 
@@ -363,7 +364,7 @@ impl BindGroupVisualizer {
             BindingTypeConfig::StorageBuffer { .. } => Color32::from_rgb(70, 130, 180), // Steel Blue
             BindingTypeConfig::Texture => Color32::from_rgb(255, 140, 0), // Dark Orange
             BindingTypeConfig::Sampler => Color32::from_rgb(218, 165, 32), // Goldenrod
-            BindingTypeConfig::StorageTexture => Color32::from_rgb(220, 20, 60), // Crimson
+            BindingTypeConfig::StorageTexture { .. } => Color32::from_rgb(220, 20, 60), // Crimson
         }
     }
 
@@ -388,7 +389,12 @@ impl BindGroupVisualizer {
             ),
             ("Texture", BindingTypeConfig::Texture),
             ("Sampler", BindingTypeConfig::Sampler),
-            ("Storage Texture", BindingTypeConfig::StorageTexture),
+            (
+                "Storage Texture",
+                BindingTypeConfig::StorageTexture {
+                    access: StorageTextureAccess::WriteOnly,
+                },
+            ),
         ];
 
         for (i, (name, binding_type)) in binding_types.iter().enumerate() {
@@ -0,0 +1,912 @@
+//! Many materials in one draw call through a texture binding array
+//!
+//! A grid of cubes uses [`MATERIAL_COUNT`] different textures, picked per
+//! instance by a material index. With [`BINDING_ARRAY_FEATURES`] every
+//! texture sits in one `binding_array<texture_2d<f32>, N>` of a single bind
+//! group and each fragment indexes it with its own material, which is what
+//! non-uniform indexing allows. Without them the same textures are packed
+//! into one atlas with gutters around every cell, and the fragment shader
+//! remaps its UVs into the material's cell instead. Both paths bind one
+//! bind group and issue one draw; [`binding_array_support`] says why the
+//! atlas is used when it is.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::instanced_cubes::cube_mesh;
+use crate::math_utils::Mat4;
+use crate::upload_belt;
+use std::num::NonZeroU32;
+use wgpu::util::DeviceExt;
+
+/// Features a device needs for the binding array path
+pub const BINDING_ARRAY_FEATURES: wgpu::Features = wgpu::Features::TEXTURE_BINDING_ARRAY
+    .union(wgpu::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING);
+
+/// Distinct material textures
+pub const MATERIAL_COUNT: u32 = 16;
+
+/// Width and height of every material texture
+pub const TEXTURE_SIZE: u32 = 64;
+
+/// Texels repeated around each atlas cell so filtering never reads the
+/// neighbouring material
+pub const ATLAS_GUTTER: u32 = 4;
+
+/// Cells per atlas row
+pub const ATLAS_COLUMNS: u32 = 4;
+
+const ATLAS_CELL: u32 = TEXTURE_SIZE + 2 * ATLAS_GUTTER;
+
+/// Width and height of the atlas
+pub const ATLAS_SIZE: u32 = ATLAS_CELL * ATLAS_COLUMNS;
+
+/// Cubes along each side of the grid
+const GRID_SIDE: u32 = 8;
+
+/// Distance between neighbouring cube centers
+const SPACING: f32 = 1.6;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub const BINDING_ARRAY_SHADER: &str = r#"// Bindless Materials Example: binding array path
+// All 16 material textures are one binding, indexed per fragment
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    time: f32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var material_sampler: sampler;
+@group(0) @binding(2) var textures: binding_array<texture_2d<f32>, 16>;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+struct InstanceInput {
+    @location(3) offset: vec3<f32>,
+    @location(4) material: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) @interpolate(flat) material: u32,
+}
+
+fn rotate_y(v: vec3<f32>, angle: f32) -> vec3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec3<f32>(c * v.x + s * v.z, v.y, -s * v.x + c * v.z);
+}
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    let angle = uniforms.time * 0.5 + f32(instance.material) * 0.4;
+    var out: VertexOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(rotate_y(vertex.position, angle) + instance.offset, 1.0);
+    out.normal = rotate_y(vertex.normal, angle);
+    out.uv = vertex.uv;
+    out.material = instance.material;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Neighbouring fragments of one draw can index different textures,
+    // which needs SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
+    let base = textureSample(textures[in.material], material_sampler, in.uv).rgb;
+    let light = max(dot(normalize(in.normal), normalize(vec3<f32>(0.4, 0.8, 0.5))), 0.0);
+    return vec4<f32>(base * (0.3 + 0.7 * light), 1.0);
+}
+"#;
+
+pub const ATLAS_SHADER: &str = r#"// Bindless Materials Example: texture atlas path
+// All 16 material textures are cells of one atlas texture
+
+const ATLAS_COLUMNS: u32 = 4u;
+const TEXTURE_SIZE: f32 = 64.0;
+const ATLAS_GUTTER: f32 = 4.0;
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    time: f32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var material_sampler: sampler;
+@group(0) @binding(2) var atlas: texture_2d<f32>;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+struct InstanceInput {
+    @location(3) offset: vec3<f32>,
+    @location(4) material: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) @interpolate(flat) material: u32,
+}
+
+fn rotate_y(v: vec3<f32>, angle: f32) -> vec3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec3<f32>(c * v.x + s * v.z, v.y, -s * v.x + c * v.z);
+}
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    let angle = uniforms.time * 0.5 + f32(instance.material) * 0.4;
+    var out: VertexOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(rotate_y(vertex.position, angle) + instance.offset, 1.0);
+    out.normal = rotate_y(vertex.normal, angle);
+    out.uv = vertex.uv;
+    out.material = instance.material;
+    return out;
+}
+
+// Where `uv` of a material lands in the atlas, inside the cell's gutter
+fn atlas_uv(material: u32, uv: vec2<f32>) -> vec2<f32> {
+    let cell_size = TEXTURE_SIZE + 2.0 * ATLAS_GUTTER;
+    let cell = vec2<f32>(f32(material % ATLAS_COLUMNS), f32(material / ATLAS_COLUMNS));
+    let texel = cell * cell_size + ATLAS_GUTTER + clamp(uv, vec2<f32>(0.0), vec2<f32>(1.0)) * TEXTURE_SIZE;
+    return texel / (cell_size * f32(ATLAS_COLUMNS));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let base = textureSample(atlas, material_sampler, atlas_uv(in.material, in.uv)).rgb;
+    let light = max(dot(normalize(in.normal), normalize(vec3<f32>(0.4, 0.8, 0.5))), 0.0);
+    return vec4<f32>(base * (0.3 + 0.7 * light), 1.0);
+}
+"#;
+
+/// How the material textures reach the fragment shader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialPath {
+    /// One binding array of textures indexed by material
+    BindingArray,
+    /// One atlas texture with a cell per material
+    Atlas,
+}
+
+impl MaterialPath {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MaterialPath::BindingArray => "Texture binding array",
+            MaterialPath::Atlas => "Texture atlas",
+        }
+    }
+
+    pub fn shader(&self) -> &'static str {
+        match self {
+            MaterialPath::BindingArray => BINDING_ARRAY_SHADER,
+            MaterialPath::Atlas => ATLAS_SHADER,
+        }
+    }
+}
+
+/// Why the atlas path is drawing
+#[derive(Debug, Clone, PartialEq)]
+pub enum FallbackReason {
+    /// No `TEXTURE_BINDING_ARRAY` feature
+    NoBindingArrays,
+    /// Binding arrays, but only indexed by dynamically uniform values
+    NoNonUniformIndexing,
+    /// A binding array can't hold every material
+    TooFewTextures { max: u32 },
+    /// The atlas was picked even though binding arrays work
+    Chosen,
+}
+
+impl std::fmt::Display for FallbackReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FallbackReason::NoBindingArrays => write!(
+                f,
+                "The device has no TEXTURE_BINDING_ARRAY feature, so a bind group can't hold \
+                 an array of textures"
+            ),
+            FallbackReason::NoNonUniformIndexing => write!(
+                f,
+                "The device has TEXTURE_BINDING_ARRAY but not \
+                 SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING, so fragments \
+                 of one draw can't each pick their own texture"
+            ),
+            FallbackReason::TooFewTextures { max } => write!(
+                f,
+                "A shader stage can sample {} textures from a binding array, fewer than the {} \
+                 materials",
+                max, MATERIAL_COUNT
+            ),
+            FallbackReason::Chosen => write!(f, "The texture atlas was chosen in the controls"),
+        }
+    }
+}
+
+/// Whether a device with `features` and `limits` can draw the binding
+/// array path
+pub fn binding_array_support(
+    features: wgpu::Features,
+    limits: &wgpu::Limits,
+) -> Result<(), FallbackReason> {
+    if !features.contains(wgpu::Features::TEXTURE_BINDING_ARRAY) {
+        return Err(FallbackReason::NoBindingArrays);
+    }
+    if !features.contains(BINDING_ARRAY_FEATURES) {
+        return Err(FallbackReason::NoNonUniformIndexing);
+    }
+    let max = limits
+        .max_binding_array_elements_per_shader_stage
+        .min(limits.max_sampled_textures_per_shader_stage);
+    if max < MATERIAL_COUNT {
+        return Err(FallbackReason::TooFewTextures { max });
+    }
+    Ok(())
+}
+
+/// Opt `descriptor` into binding arrays when `adapter` can draw the binding
+/// array path
+///
+/// The binding array limit is zero by default; it is raised to fit the
+/// materials.
+pub fn request_binding_arrays(adapter: &wgpu::Adapter, descriptor: &mut wgpu::DeviceDescriptor) {
+    let limits = adapter.limits();
+    if binding_array_support(adapter.features(), &limits).is_err() {
+        return;
+    }
+    descriptor.required_features |= BINDING_ARRAY_FEATURES;
+    let required = &mut descriptor.required_limits;
+    required.max_binding_array_elements_per_shader_stage = required
+        .max_binding_array_elements_per_shader_stage
+        .max(MATERIAL_COUNT);
+    required.max_sampled_textures_per_shader_stage = required
+        .max_sampled_textures_per_shader_stage
+        .max(MATERIAL_COUNT);
+}
+
+fn hsv(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let channel = |offset: f32| {
+        let k = (offset + hue * 6.0) % 6.0;
+        value - value * saturation * (k.min(4.0 - k).clamp(0.0, 1.0))
+    };
+    [channel(5.0), channel(3.0), channel(1.0)]
+}
+
+/// RGBA texels of material `material`: one of four patterns in two shades
+/// of a hue of its own
+pub fn material_texels(material: u32) -> Vec<[u8; 4]> {
+    let hue = material as f32 / MATERIAL_COUNT as f32;
+    let light = hsv(hue, 0.55, 0.95);
+    let dark = hsv(hue, 0.85, 0.45);
+    let size = TEXTURE_SIZE as f32;
+    (0..TEXTURE_SIZE * TEXTURE_SIZE)
+        .map(|i| {
+            let (x, y) = (
+                (i % TEXTURE_SIZE) as f32 + 0.5,
+                (i / TEXTURE_SIZE) as f32 + 0.5,
+            );
+            let (u, v) = (x / size, y / size);
+            let on = match material % 4 {
+                // Checkerboard
+                0 => ((u * 4.0) as u32 + (v * 4.0) as u32).is_multiple_of(2),
+                // Diagonal stripes
+                1 => ((u + v) * 5.0).fract() < 0.5,
+                // Dots
+                2 => {
+                    let (du, dv) = ((u * 4.0).fract() - 0.5, (v * 4.0).fract() - 0.5);
+                    du * du + dv * dv < 0.09
+                }
+                // Rings
+                _ => {
+                    let (du, dv) = (u - 0.5, v - 0.5);
+                    ((du * du + dv * dv).sqrt() * 8.0).fract() < 0.5
+                }
+            };
+            let color = if on { light } else { dark };
+            let [r, g, b] = color.map(|c| (c * 255.0).round() as u8);
+            [r, g, b, 255]
+        })
+        .collect()
+}
+
+/// Pack the textures of `materials` into an [`ATLAS_SIZE`] square, each in
+/// its cell surrounded by [`ATLAS_GUTTER`] copies of its edge texels
+pub fn build_atlas(materials: &[Vec<[u8; 4]>]) -> Vec<[u8; 4]> {
+    let mut atlas = vec![[0, 0, 0, 255]; (ATLAS_SIZE * ATLAS_SIZE) as usize];
+    for (material, texels) in materials.iter().enumerate() {
+        let material = material as u32;
+        let origin = [
+            material % ATLAS_COLUMNS * ATLAS_CELL,
+            material / ATLAS_COLUMNS * ATLAS_CELL,
+        ];
+        for y in 0..ATLAS_CELL {
+            for x in 0..ATLAS_CELL {
+                // Clamp to the texture to fill the gutter with its edges
+                let source = |c: u32| c.saturating_sub(ATLAS_GUTTER).min(TEXTURE_SIZE - 1);
+                let texel = texels[(source(y) * TEXTURE_SIZE + source(x)) as usize];
+                atlas[((origin[1] + y) * ATLAS_SIZE + origin[0] + x) as usize] = texel;
+            }
+        }
+    }
+    atlas
+}
+
+/// Vertex of the textured cube
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MaterialVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+}
+
+/// Per-instance attributes: where the cube is and which material it uses
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MaterialInstance {
+    pub offset: [f32; 3],
+    pub material: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: Mat4,
+    time: f32,
+    _padding: [f32; 3],
+}
+
+/// The cube mesh of [`cube_mesh`] with every face mapped to the whole texture
+pub fn textured_cube() -> (Vec<MaterialVertex>, Vec<u16>) {
+    let (cube, indices) = cube_mesh();
+    // Corners go round each face in the same order
+    let corners = [[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]];
+    let vertices = cube
+        .iter()
+        .zip(corners.iter().cycle())
+        .map(|(vertex, &uv)| MaterialVertex {
+            position: vertex.position,
+            normal: vertex.normal,
+            uv,
+        })
+        .collect();
+    (vertices, indices)
+}
+
+/// A flat grid of cubes cycling through the materials
+pub fn material_grid() -> Vec<MaterialInstance> {
+    let center = (GRID_SIDE - 1) as f32 / 2.0;
+    (0..GRID_SIDE * GRID_SIDE)
+        .map(|i| {
+            let (x, z) = (i % GRID_SIDE, i / GRID_SIDE);
+            MaterialInstance {
+                offset: [
+                    (x as f32 - center) * SPACING,
+                    0.0,
+                    (z as f32 - center) * SPACING,
+                ],
+                // Step diagonally so neighbours never share a material
+                material: (x + 3 * z) % MATERIAL_COUNT,
+            }
+        })
+        .collect()
+}
+
+/// Radius of a sphere around the origin containing the grid
+pub fn grid_radius() -> f32 {
+    GRID_SIDE as f32 * SPACING * std::f32::consts::FRAC_1_SQRT_2
+}
+
+/// Renderer of the bindless materials example
+pub struct MaterialGrid {
+    path: MaterialPath,
+    fallback: Option<FallbackReason>,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    instance_buffer: wgpu::Buffer,
+    instance_count: u32,
+    depth_view: wgpu::TextureView,
+    depth_size: (u32, u32),
+}
+
+impl MaterialGrid {
+    /// Create the grid with the binding array path when `device` supports
+    /// it and `prefer_binding_array` is set, and the atlas otherwise
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+        prefer_binding_array: bool,
+    ) -> Self {
+        let fallback = match binding_array_support(device.features(), &device.limits()) {
+            Ok(()) if prefer_binding_array => None,
+            Ok(()) => Some(FallbackReason::Chosen),
+            Err(reason) => Some(reason),
+        };
+        let path = if fallback.is_none() {
+            MaterialPath::BindingArray
+        } else {
+            MaterialPath::Atlas
+        };
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Bindless Materials Shader"),
+            source: wgpu::ShaderSource::Wgsl(path.shader().into()),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Bindless Materials Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let (vertices, indices) = textured_cube();
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bindless Materials Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bindless Materials Index Buffer"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let instances = material_grid();
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bindless Materials Instance Buffer"),
+            contents: bytemuck::cast_slice(&instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Bindless Materials Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let materials: Vec<_> = (0..MATERIAL_COUNT).map(material_texels).collect();
+        let views: Vec<wgpu::TextureView> = match path {
+            MaterialPath::BindingArray => materials
+                .iter()
+                .map(|texels| upload_texture(device, queue, TEXTURE_SIZE, texels))
+                .collect(),
+            MaterialPath::Atlas => {
+                vec![upload_texture(
+                    device,
+                    queue,
+                    ATLAS_SIZE,
+                    &build_atlas(&materials),
+                )]
+            }
+        };
+        let view_refs: Vec<&wgpu::TextureView> = views.iter().collect();
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Bindless Materials Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: match path {
+                        MaterialPath::BindingArray => NonZeroU32::new(MATERIAL_COUNT),
+                        MaterialPath::Atlas => None,
+                    },
+                },
+            ],
+        });
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Bindless Materials Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: match path {
+                        MaterialPath::BindingArray => {
+                            wgpu::BindingResource::TextureViewArray(&view_refs)
+                        }
+                        MaterialPath::Atlas => wgpu::BindingResource::TextureView(view_refs[0]),
+                    },
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Bindless Materials Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Bindless Materials Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<MaterialVertex>() as u64,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![
+                            0 => Float32x3,
+                            1 => Float32x3,
+                            2 => Float32x2
+                        ],
+                    },
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<MaterialInstance>() as u64,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![3 => Float32x3, 4 => Uint32],
+                    },
+                ],
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Self {
+            path,
+            fallback,
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+            instance_buffer,
+            instance_count: instances.len() as u32,
+            depth_view: create_depth_view(device, 1, 1),
+            depth_size: (1, 1),
+        }
+    }
+
+    pub fn path(&self) -> MaterialPath {
+        self.path
+    }
+
+    /// Why the atlas is drawing, or `None` on the binding array path
+    pub fn fallback(&self) -> Option<&FallbackReason> {
+        self.fallback.as_ref()
+    }
+
+    /// Textures in the bind group
+    pub fn textures_bound(&self) -> u32 {
+        match self.path {
+            MaterialPath::BindingArray => MATERIAL_COUNT,
+            MaterialPath::Atlas => 1,
+        }
+    }
+
+    pub fn instance_count(&self) -> u32 {
+        self.instance_count
+    }
+
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: Mat4, time: f32) {
+        let uniforms = Uniforms {
+            view_proj,
+            time,
+            _padding: [0.0; 3],
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+    }
+
+    /// Draw every cube with one bind group and one draw call
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        if self.depth_size != (width, height) {
+            self.depth_view = create_depth_view(device, width, height);
+            self.depth_size = (width, height);
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Bindless Materials Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Bindless Materials Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            tracker.record(ApiCategory::RenderPass, "set_pipeline");
+            render_pass.set_pipeline(&self.pipeline);
+            tracker.record(ApiCategory::RenderPass, "set_bind_group");
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+            tracker.record(ApiCategory::RenderPass, "set_index_buffer");
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            tracker.record(ApiCategory::RenderPass, "draw_indexed");
+            render_pass.draw_indexed(0..self.index_count, 0, 0..self.instance_count);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+}
+
+fn upload_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    size: u32,
+    texels: &[[u8; 4]],
+) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    let texture = device.create_texture_with_data(
+        queue,
+        &wgpu::TextureDescriptor {
+            label: Some("Bindless Materials Texture"),
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        bytemuck::cast_slice(texels),
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Bindless Materials Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str, capabilities: naga::valid::Capabilities) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(naga::valid::ValidationFlags::all(), capabilities)
+            .validate(&module)
+            .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        // The newer naga used for validation wants binding arrays enabled
+        // explicitly; the one inside wgpu doesn't know the directive yet
+        validate(
+            &format!("enable wgpu_binding_array;\n{}", BINDING_ARRAY_SHADER),
+            naga::valid::Capabilities::TEXTURE_AND_SAMPLER_BINDING_ARRAY
+                | naga::valid::Capabilities::TEXTURE_AND_SAMPLER_BINDING_ARRAY_NON_UNIFORM_INDEXING,
+        );
+        validate(ATLAS_SHADER, naga::valid::Capabilities::empty());
+        assert!(BINDING_ARRAY_SHADER.contains(&format!(
+            "binding_array<texture_2d<f32>, {}>",
+            MATERIAL_COUNT
+        )));
+        for constant in [
+            format!("const ATLAS_COLUMNS: u32 = {}u;", ATLAS_COLUMNS),
+            format!("const TEXTURE_SIZE: f32 = {:.1};", TEXTURE_SIZE as f32),
+            format!("const ATLAS_GUTTER: f32 = {:.1};", ATLAS_GUTTER as f32),
+        ] {
+            assert!(ATLAS_SHADER.contains(&constant), "{}", constant);
+        }
+    }
+
+    #[test]
+    fn test_binding_array_needs_non_uniform_indexing() {
+        let mut limits = wgpu::Limits {
+            max_binding_array_elements_per_shader_stage: 500_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            binding_array_support(wgpu::Features::empty(), &limits),
+            Err(FallbackReason::NoBindingArrays)
+        );
+        assert_eq!(
+            binding_array_support(wgpu::Features::TEXTURE_BINDING_ARRAY, &limits),
+            Err(FallbackReason::NoNonUniformIndexing)
+        );
+        assert_eq!(
+            binding_array_support(BINDING_ARRAY_FEATURES, &limits),
+            Ok(())
+        );
+
+        // The default limits leave no room for binding arrays
+        assert_eq!(
+            binding_array_support(BINDING_ARRAY_FEATURES, &wgpu::Limits::default()),
+            Err(FallbackReason::TooFewTextures { max: 0 })
+        );
+        limits.max_sampled_textures_per_shader_stage = 8;
+        assert_eq!(
+            binding_array_support(BINDING_ARRAY_FEATURES, &limits),
+            Err(FallbackReason::TooFewTextures { max: 8 })
+        );
+    }
+
+    #[test]
+    fn test_materials_differ() {
+        let materials: Vec<_> = (0..MATERIAL_COUNT).map(material_texels).collect();
+        for (i, texels) in materials.iter().enumerate() {
+            assert_eq!(texels.len(), (TEXTURE_SIZE * TEXTURE_SIZE) as usize);
+            assert!(materials[..i].iter().all(|other| other != texels));
+        }
+    }
+
+    #[test]
+    fn test_atlas_cells_hold_materials_and_gutters() {
+        let materials: Vec<_> = (0..MATERIAL_COUNT).map(material_texels).collect();
+        let atlas = build_atlas(&materials);
+        assert_eq!(atlas.len(), (ATLAS_SIZE * ATLAS_SIZE) as usize);
+        let at = |x: u32, y: u32| atlas[(y * ATLAS_SIZE + x) as usize];
+
+        let material = 6;
+        let texels = &materials[material as usize];
+        let origin = [
+            material % ATLAS_COLUMNS * ATLAS_CELL,
+            material / ATLAS_COLUMNS * ATLAS_CELL,
+        ];
+        for y in 0..TEXTURE_SIZE {
+            for x in 0..TEXTURE_SIZE {
+                assert_eq!(
+                    at(origin[0] + ATLAS_GUTTER + x, origin[1] + ATLAS_GUTTER + y),
+                    texels[(y * TEXTURE_SIZE + x) as usize]
+                );
+            }
+        }
+        // The gutter repeats the corner and edge texels
+        assert_eq!(at(origin[0], origin[1]), texels[0]);
+        let last = ATLAS_CELL - 1;
+        assert_eq!(
+            at(origin[0] + last, origin[1] + ATLAS_GUTTER + 5),
+            texels[(5 * TEXTURE_SIZE + TEXTURE_SIZE - 1) as usize]
+        );
+    }
+
+    #[test]
+    fn test_grid_neighbours_use_different_materials() {
+        let grid = material_grid();
+        assert_eq!(grid.len(), (GRID_SIDE * GRID_SIDE) as usize);
+        for (i, instance) in grid.iter().enumerate() {
+            assert!(instance.material < MATERIAL_COUNT);
+            let i = i as u32;
+            if i % GRID_SIDE + 1 < GRID_SIDE {
+                assert_ne!(instance.material, grid[i as usize + 1].material);
+            }
+            if i + GRID_SIDE < GRID_SIDE * GRID_SIDE {
+                assert_ne!(instance.material, grid[(i + GRID_SIDE) as usize].material);
+            }
+        }
+        let used: std::collections::HashSet<_> = grid.iter().map(|i| i.material).collect();
+        assert_eq!(used.len(), MATERIAL_COUNT as usize);
+        assert!(grid.iter().all(|i| {
+            let [x, _, z] = i.offset;
+            (x * x + z * z).sqrt() + 0.87 <= grid_radius()
+        }));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<MaterialVertex>(), 32);
+        assert_eq!(std::mem::size_of::<MaterialInstance>(), 16);
+        assert_eq!(std::mem::size_of::<Uniforms>(), 80);
+    }
+}
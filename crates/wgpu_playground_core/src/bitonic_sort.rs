@@ -0,0 +1,830 @@
+//! Bitonic sort for the GPU sorting example
+//!
+//! A bitonic sorting network sorts a power-of-two number of keys with a
+//! fixed sequence of compare-and-swap steps: for every block size `k` from 2
+//! up to the key count, steps with a halving partner distance `j` merge pairs
+//! of sorted runs into runs of `k` keys. Every step is independent of the
+//! data, so the host plans the whole schedule up front:
+//!
+//! - [`SortStrategy::Workgroup`] sorts blocks of 256 keys in one dispatch and
+//!   runs all steps with `j < 256` of a block size in `var<workgroup>`
+//!   memory, so only the long-distance steps touch global memory
+//! - [`SortStrategy::Global`] dispatches every step over global memory
+//!
+//! Keys are `u32`, or `f32` compared through their bits with the order of
+//! [`f32::total_cmp`]; other key counts are padded with the largest key.
+//! Results are checked against the standard library's sort.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::parallel_algorithms::first_mismatch;
+use std::time::{Duration, Instant};
+use wgpu::util::DeviceExt;
+
+/// Invocations per workgroup, and keys sorted in workgroup memory at once
+pub const WORKGROUP_SIZE: u32 = 256;
+
+/// Fewest keys the example sorts
+pub const MIN_KEYS: u32 = 1 << 10;
+
+/// Most keys the example sorts
+///
+/// One invocation per key stays within the 65535 workgroups a single
+/// dispatch dimension allows.
+pub const MAX_KEYS: u32 = 1 << 22;
+
+/// Keys of the pass visualization, one pixel column each
+pub const VISUALIZED_KEYS: u32 = 512;
+
+pub const BITONIC_SORT_SHADER: &str = r#"// Bitonic Sort Example
+// Every step compares each key with its partner `j` places away and swaps
+// them into the direction of its block of `k` keys. `sort_blocks` and
+// `merge_local` run many steps in workgroup memory, `merge_global` runs one
+// step over the whole buffer.
+
+struct Params {
+    k: u32,
+    j: u32,
+    key_type: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+const KEY_F32: u32 = 1u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> keys: array<u32>;
+
+var<workgroup> block: array<u32, WORKGROUP_SIZE>;
+
+// A u32 that orders like the key: floats order like their bits once the
+// sign bit is flipped, and negative floats once every bit is flipped
+fn order(bits: u32) -> u32 {
+    if params.key_type == KEY_F32 {
+        let negative = (bits >> 31u) == 1u;
+        return bits ^ select(0x80000000u, 0xffffffffu, negative);
+    }
+    return bits;
+}
+
+// Whether the keys at `index` and its higher partner must be swapped
+fn out_of_order(a: u32, b: u32, index: u32, k: u32) -> bool {
+    let ascending = (index & k) == 0u;
+    return (order(a) > order(b)) == ascending;
+}
+
+// One step within the block, followed by a barrier so the next step reads
+// its results
+fn local_step(local: u32, index: u32, k: u32, j: u32) {
+    let partner = local ^ j;
+    if partner > local {
+        let a = block[local];
+        let b = block[partner];
+        if out_of_order(a, b, index, k) {
+            block[local] = b;
+            block[partner] = a;
+        }
+    }
+    workgroupBarrier();
+}
+
+@compute @workgroup_size(256)
+fn sort_blocks(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+) {
+    block[local_id.x] = keys[id.x];
+    workgroupBarrier();
+    for (var k = 2u; k <= WORKGROUP_SIZE; k *= 2u) {
+        for (var j = k / 2u; j > 0u; j /= 2u) {
+            local_step(local_id.x, id.x, k, j);
+        }
+    }
+    keys[id.x] = block[local_id.x];
+}
+
+@compute @workgroup_size(256)
+fn merge_local(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+) {
+    block[local_id.x] = keys[id.x];
+    workgroupBarrier();
+    for (var j = WORKGROUP_SIZE / 2u; j > 0u; j /= 2u) {
+        local_step(local_id.x, id.x, params.k, j);
+    }
+    keys[id.x] = block[local_id.x];
+}
+
+@compute @workgroup_size(256)
+fn merge_global(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    let partner = index ^ params.j;
+    if partner > index {
+        let a = keys[index];
+        let b = keys[partner];
+        if out_of_order(a, b, index, params.k) {
+            keys[index] = b;
+            keys[partner] = a;
+        }
+    }
+}
+"#;
+
+/// The type the sorted bits are compared as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    U32,
+    F32,
+}
+
+impl KeyType {
+    pub fn all() -> &'static [KeyType] {
+        &[KeyType::U32, KeyType::F32]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyType::U32 => "u32",
+            KeyType::F32 => "f32",
+        }
+    }
+
+    /// Matches the `KEY_` constants of the shader
+    fn shader_value(&self) -> u32 {
+        match self {
+            KeyType::U32 => 0,
+            KeyType::F32 => 1,
+        }
+    }
+
+    /// A `u32` that orders like the key, as the shader's `order`
+    pub fn order(&self, bits: u32) -> u32 {
+        match self {
+            KeyType::U32 => bits,
+            KeyType::F32 if bits >> 31 == 1 => !bits,
+            KeyType::F32 => bits ^ 0x8000_0000,
+        }
+    }
+
+    /// Bits of the largest key, which padding sorts behind every real key
+    fn padding(&self) -> u32 {
+        match self {
+            KeyType::U32 => u32::MAX,
+            // A positive NaN, last in the order of `f32::total_cmp`
+            KeyType::F32 => 0x7fff_ffff,
+        }
+    }
+}
+
+/// How the GPU runs the sorting network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortStrategy {
+    /// Steps with partners in the same block run in workgroup memory
+    Workgroup,
+    /// Every step is a dispatch over global memory
+    Global,
+}
+
+impl SortStrategy {
+    pub fn all() -> &'static [SortStrategy] {
+        &[SortStrategy::Workgroup, SortStrategy::Global]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortStrategy::Workgroup => "Workgroup memory for short steps",
+            SortStrategy::Global => "Global memory only",
+        }
+    }
+}
+
+/// One dispatch of the sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortPass {
+    /// Every step of block sizes up to [`WORKGROUP_SIZE`]
+    SortBlocks,
+    /// The steps of block size `k` with partners in the same workgroup
+    MergeLocal { k: u32 },
+    /// One step of block size `k` with partner distance `j`
+    MergeGlobal { k: u32, j: u32 },
+}
+
+impl SortPass {
+    /// Block size and partner distance of the shader's `Params`
+    fn params(&self) -> (u32, u32) {
+        match *self {
+            SortPass::SortBlocks => (0, 0),
+            SortPass::MergeLocal { k } => (k, 0),
+            SortPass::MergeGlobal { k, j } => (k, j),
+        }
+    }
+}
+
+/// Dispatches sorting `len` keys, a power of two of at least
+/// [`WORKGROUP_SIZE`]
+pub fn schedule(len: u32, strategy: SortStrategy) -> Vec<SortPass> {
+    let mut passes = Vec::new();
+    let mut k = 2;
+    if strategy == SortStrategy::Workgroup {
+        passes.push(SortPass::SortBlocks);
+        k = WORKGROUP_SIZE * 2;
+    }
+    while k <= len {
+        let mut j = k / 2;
+        while j > 0 {
+            if strategy == SortStrategy::Workgroup && j < WORKGROUP_SIZE {
+                passes.push(SortPass::MergeLocal { k });
+                break;
+            }
+            passes.push(SortPass::MergeGlobal { k, j });
+            j /= 2;
+        }
+        k *= 2;
+    }
+    passes
+}
+
+/// Adjustable parameters of the sort example
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortParams {
+    /// Number of keys, from [`MIN_KEYS`] to [`MAX_KEYS`]
+    pub key_count: u32,
+    pub key_type: KeyType,
+}
+
+impl Default for SortParams {
+    fn default() -> Self {
+        Self {
+            key_count: 1 << 20,
+            key_type: KeyType::U32,
+        }
+    }
+}
+
+/// Bits of pseudo-random keys, the same for the same `seed`
+///
+/// `f32` keys lie between -1000 and 1000.
+pub fn random_keys(count: u32, key_type: KeyType, seed: u32) -> Vec<u32> {
+    // xorshift32, which never leaves zero
+    let mut state = seed.max(1);
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            match key_type {
+                KeyType::U32 => state,
+                KeyType::F32 => {
+                    ((state as f64 / u32::MAX as f64 * 2000.0 - 1000.0) as f32).to_bits()
+                }
+            }
+        })
+        .collect()
+}
+
+/// The keys sorted on the CPU with the standard library
+pub fn cpu_sort(keys: &[u32], key_type: KeyType) -> Vec<u32> {
+    match key_type {
+        KeyType::U32 => {
+            let mut sorted = keys.to_vec();
+            sorted.sort_unstable();
+            sorted
+        }
+        KeyType::F32 => {
+            let mut sorted: Vec<f32> = keys.iter().map(|&bits| f32::from_bits(bits)).collect();
+            sorted.sort_unstable_by(f32::total_cmp);
+            sorted.into_iter().map(f32::to_bits).collect()
+        }
+    }
+}
+
+/// Color every key of the snapshots by its place between the smallest and
+/// the largest key, one row per snapshot
+pub fn snapshot_rgba(snapshots: &[Vec<u32>], key_type: KeyType) -> Vec<u8> {
+    let orders = || snapshots.iter().flatten().map(|&bits| key_type.order(bits));
+    let (Some(min), Some(max)) = (orders().min(), orders().max()) else {
+        return Vec::new();
+    };
+    let range = (max - min).max(1) as f32;
+    orders()
+        .flat_map(|order| {
+            let t = (order - min) as f32 / range;
+            [
+                (t * 255.0) as u8,
+                (40.0 + t * 180.0) as u8,
+                ((1.0 - t) * 255.0) as u8,
+                255,
+            ]
+        })
+        .collect()
+}
+
+/// Output and timing of one GPU sort
+#[derive(Debug, Clone)]
+pub struct SortRun {
+    pub output: Vec<u32>,
+    /// From submitting the dispatches until the GPU finished them
+    pub gpu_time: Duration,
+    pub passes: usize,
+}
+
+/// Timing and verification of one strategy in a [`SortComparison`]
+#[derive(Debug, Clone)]
+pub struct SortStrategyResult {
+    pub strategy: SortStrategy,
+    pub gpu_time: Duration,
+    pub passes: usize,
+    /// First key that differs from the CPU sort, `None` if all match
+    pub mismatch: Option<usize>,
+}
+
+/// Both strategies timed against the CPU sort on the same keys
+#[derive(Debug, Clone)]
+pub struct SortComparison {
+    pub params: SortParams,
+    pub cpu_time: Duration,
+    pub strategies: Vec<SortStrategyResult>,
+}
+
+impl SortComparison {
+    /// How many times faster the workgroup strategy ran than global memory only
+    pub fn speedup(&self) -> Option<f64> {
+        let time = |strategy| {
+            self.strategies
+                .iter()
+                .find(|result| result.strategy == strategy)
+                .map(|result| result.gpu_time.as_secs_f64())
+        };
+        match (time(SortStrategy::Workgroup), time(SortStrategy::Global)) {
+            (Some(fast), Some(slow)) if fast > 0.0 => Some(slow / fast),
+            _ => None,
+        }
+    }
+
+    /// Whether every strategy matched the CPU
+    pub fn verified(&self) -> bool {
+        self.strategies
+            .iter()
+            .all(|result| result.mismatch.is_none())
+    }
+}
+
+/// Size of the shader's `Params`, padded
+const PARAMS_SIZE: u64 = 16;
+
+/// Pipelines of the bitonic sort
+pub struct BitonicSorter {
+    layout: wgpu::BindGroupLayout,
+    sort_blocks: wgpu::ComputePipeline,
+    merge_local: wgpu::ComputePipeline,
+    merge_global: wgpu::ComputePipeline,
+}
+
+impl BitonicSorter {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Bitonic Sort Bind Group Layout"),
+            entries: &[
+                // One slot of the params buffer per pass
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: wgpu::BufferSize::new(PARAMS_SIZE),
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Bitonic Sort Pipeline Layout"),
+            bind_group_layouts: &[Some(&layout)],
+            immediate_size: 0,
+        });
+
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Bitonic Sort Shader"),
+            source: wgpu::ShaderSource::Wgsl(BITONIC_SORT_SHADER.into()),
+        });
+        let create_pipeline = |entry_point| {
+            tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+
+        Self {
+            sort_blocks: create_pipeline("sort_blocks"),
+            merge_local: create_pipeline("merge_local"),
+            merge_global: create_pipeline("merge_global"),
+            layout,
+        }
+    }
+
+    fn pipeline(&self, pass: SortPass) -> &wgpu::ComputePipeline {
+        match pass {
+            SortPass::SortBlocks => &self.sort_blocks,
+            SortPass::MergeLocal { .. } => &self.merge_local,
+            SortPass::MergeGlobal { .. } => &self.merge_global,
+        }
+    }
+
+    /// Upload `keys` padded to a power of two, with the params of every pass
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        keys: &[u32],
+        key_type: KeyType,
+        strategy: SortStrategy,
+    ) -> Result<PreparedSort, String> {
+        let count = keys.len() as u32;
+        if !(MIN_KEYS..=MAX_KEYS).contains(&count) && count != VISUALIZED_KEYS {
+            return Err(format!(
+                "Key count {} is outside {}..={}",
+                count, MIN_KEYS, MAX_KEYS
+            ));
+        }
+        let len = count.next_power_of_two().max(WORKGROUP_SIZE);
+        let max = device.limits().max_storage_buffer_binding_size;
+        if len as u64 * 4 > max {
+            return Err(format!(
+                "{} keys need {} bytes, more than the device's storage binding limit of {}",
+                len,
+                len as u64 * 4,
+                max
+            ));
+        }
+        let passes = schedule(len, strategy);
+
+        let mut padded = keys.to_vec();
+        padded.resize(len as usize, key_type.padding());
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Buffer, "create_buffer_init");
+        let keys = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bitonic Sort Keys"),
+            contents: bytemuck::cast_slice(&padded),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        });
+
+        let stride = PARAMS_SIZE
+            .next_multiple_of(device.limits().min_uniform_buffer_offset_alignment as u64);
+        let mut params = vec![0u8; (stride * passes.len().max(1) as u64) as usize];
+        for (pass, slot) in passes.iter().zip(params.chunks_mut(stride as usize)) {
+            let (k, j) = pass.params();
+            let values = [k, j, key_type.shader_value(), 0];
+            slot[..PARAMS_SIZE as usize].copy_from_slice(bytemuck::cast_slice(&values));
+        }
+        tracker.record(ApiCategory::Buffer, "create_buffer_init");
+        let params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bitonic Sort Params"),
+            contents: &params,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Bitonic Sort Bind Group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &params,
+                        offset: 0,
+                        size: wgpu::BufferSize::new(PARAMS_SIZE),
+                    }),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: keys.as_entire_binding(),
+                },
+            ],
+        });
+
+        Ok(PreparedSort {
+            keys,
+            count,
+            len,
+            passes,
+            bind_group,
+            stride,
+        })
+    }
+
+    /// Record pass `index` of `sort` in its own compute pass
+    fn encode_pass(&self, encoder: &mut wgpu::CommandEncoder, sort: &PreparedSort, index: usize) {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Bitonic Sort Pass"),
+            timestamp_writes: None,
+        });
+        sort.dispatch(&mut pass, self.pipeline(sort.passes[index]), index);
+    }
+
+    /// Sort `keys` on the GPU and read them back
+    ///
+    /// Only the dispatches are timed, not the upload or the readback.
+    pub fn sort(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        keys: &[u32],
+        key_type: KeyType,
+        strategy: SortStrategy,
+    ) -> Result<SortRun, String> {
+        let sort = self.prepare(device, keys, key_type, strategy)?;
+        let tracker = ApiCoverageTracker::global();
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Bitonic Sort Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Bitonic Sort Pass"),
+                timestamp_writes: None,
+            });
+            for (index, &step) in sort.passes.iter().enumerate() {
+                sort.dispatch(&mut pass, self.pipeline(step), index);
+            }
+        }
+        // Let earlier work finish so it is not part of the timed submission
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+
+        let start = Instant::now();
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(std::iter::once(encoder.finish()));
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+        let gpu_time = start.elapsed();
+
+        let bytes =
+            crate::buffer_io::read_buffer(device, queue, &sort.keys).map_err(|e| e.to_string())?;
+        let sorted: &[u32] = bytemuck::cast_slice(&bytes);
+        Ok(SortRun {
+            output: sorted[..sort.count as usize].to_vec(),
+            gpu_time,
+            passes: sort.passes.len(),
+        })
+    }
+
+    /// Sort [`VISUALIZED_KEYS`] keys one step per dispatch, returning the
+    /// keys before the first step and after every step
+    pub fn snapshots(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key_type: KeyType,
+    ) -> Result<Vec<Vec<u32>>, String> {
+        let keys = random_keys(VISUALIZED_KEYS, key_type, VISUALIZED_KEYS);
+        let sort = self.prepare(device, &keys, key_type, SortStrategy::Global)?;
+        let tracker = ApiCoverageTracker::global();
+
+        let row_size = sort.len as u64 * 4;
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let snapshots = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Bitonic Sort Snapshots"),
+            size: row_size * (sort.passes.len() as u64 + 1),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Bitonic Sort Snapshot Encoder"),
+        });
+        tracker.record(ApiCategory::CommandEncoder, "copy_buffer_to_buffer");
+        encoder.copy_buffer_to_buffer(&sort.keys, 0, &snapshots, 0, row_size);
+        for index in 0..sort.passes.len() {
+            self.encode_pass(&mut encoder, &sort, index);
+            tracker.record(ApiCategory::CommandEncoder, "copy_buffer_to_buffer");
+            encoder.copy_buffer_to_buffer(
+                &sort.keys,
+                0,
+                &snapshots,
+                row_size * (index as u64 + 1),
+                row_size,
+            );
+        }
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let bytes =
+            crate::buffer_io::read_buffer(device, queue, &snapshots).map_err(|e| e.to_string())?;
+        Ok(bytemuck::cast_slice::<u8, u32>(&bytes)
+            .chunks(sort.len as usize)
+            .map(|row| row[..sort.count as usize].to_vec())
+            .collect())
+    }
+
+    /// Time both strategies against the CPU on [`random_keys`]
+    pub fn compare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        params: SortParams,
+    ) -> Result<SortComparison, String> {
+        let count = params.key_count.clamp(MIN_KEYS, MAX_KEYS);
+        let keys = random_keys(count, params.key_type, count);
+
+        let start = Instant::now();
+        let expected = cpu_sort(&keys, params.key_type);
+        let cpu_time = start.elapsed();
+
+        let strategies = SortStrategy::all()
+            .iter()
+            .map(|&strategy| {
+                let run = self.sort(device, queue, &keys, params.key_type, strategy)?;
+                Ok(SortStrategyResult {
+                    strategy,
+                    gpu_time: run.gpu_time,
+                    passes: run.passes,
+                    mismatch: first_mismatch(&expected, &run.output),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(SortComparison {
+            params: SortParams {
+                key_count: count,
+                ..params
+            },
+            cpu_time,
+            strategies,
+        })
+    }
+}
+
+/// Uploaded keys and the dispatches sorting them
+struct PreparedSort {
+    keys: wgpu::Buffer,
+    count: u32,
+    /// Key count padded to a power of two
+    len: u32,
+    passes: Vec<SortPass>,
+    bind_group: wgpu::BindGroup,
+    /// Distance between the params of consecutive passes
+    stride: u64,
+}
+
+impl PreparedSort {
+    fn dispatch(
+        &self,
+        pass: &mut wgpu::ComputePass,
+        pipeline: &wgpu::ComputePipeline,
+        index: usize,
+    ) {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::ComputePass, "set_pipeline");
+        pass.set_pipeline(pipeline);
+        tracker.record(ApiCategory::ComputePass, "set_bind_group");
+        pass.set_bind_group(0, &self.bind_group, &[(self.stride * index as u64) as u32]);
+        tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+        pass.dispatch_workgroups(self.len / WORKGROUP_SIZE, 1, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shader_validates() {
+        let module = naga::front::wgsl::parse_str(BITONIC_SORT_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    /// Run the passes on the CPU the way the shader does
+    fn run_schedule(keys: &mut [u32], key_type: KeyType, passes: &[SortPass]) {
+        let step = |keys: &mut [u32], k: u32, j: u32| {
+            for index in 0..keys.len() as u32 {
+                let partner = index ^ j;
+                if partner > index {
+                    let (a, b) = (keys[index as usize], keys[partner as usize]);
+                    let ascending = index & k == 0;
+                    if (key_type.order(a) > key_type.order(b)) == ascending {
+                        keys.swap(index as usize, partner as usize);
+                    }
+                }
+            }
+        };
+        for pass in passes {
+            match *pass {
+                SortPass::SortBlocks => {
+                    let mut k = 2;
+                    while k <= WORKGROUP_SIZE {
+                        let mut j = k / 2;
+                        while j > 0 {
+                            step(keys, k, j);
+                            j /= 2;
+                        }
+                        k *= 2;
+                    }
+                }
+                SortPass::MergeLocal { k } => {
+                    let mut j = WORKGROUP_SIZE / 2;
+                    while j > 0 {
+                        step(keys, k, j);
+                        j /= 2;
+                    }
+                }
+                SortPass::MergeGlobal { k, j } => step(keys, k, j),
+            }
+        }
+    }
+
+    #[test]
+    fn test_schedules_sort() {
+        for &strategy in SortStrategy::all() {
+            for &key_type in KeyType::all() {
+                let mut keys = random_keys(2048, key_type, 3);
+                let expected = cpu_sort(&keys, key_type);
+                run_schedule(&mut keys, key_type, &schedule(2048, strategy));
+                assert_eq!(keys, expected, "{:?} {:?}", strategy, key_type);
+            }
+        }
+    }
+
+    #[test]
+    fn test_schedule_lengths() {
+        // log2(n) * (log2(n) + 1) / 2 steps
+        assert_eq!(schedule(1 << 10, SortStrategy::Global).len(), 55);
+        // Blocks, then per block size the global steps and one local pass
+        let passes = schedule(1 << 10, SortStrategy::Workgroup);
+        assert_eq!(
+            passes,
+            [
+                SortPass::SortBlocks,
+                SortPass::MergeGlobal { k: 512, j: 256 },
+                SortPass::MergeLocal { k: 512 },
+                SortPass::MergeGlobal { k: 1024, j: 512 },
+                SortPass::MergeGlobal { k: 1024, j: 256 },
+                SortPass::MergeLocal { k: 1024 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_f32_order_matches_total_cmp() {
+        let values = [-1000.0f32, -1.5, -0.0, 0.0, 1e-30, 2.0, 999.0];
+        for pair in values.windows(2) {
+            assert!(
+                KeyType::F32.order(pair[0].to_bits()) < KeyType::F32.order(pair[1].to_bits()),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert_eq!(
+            KeyType::F32.order(KeyType::F32.padding()),
+            u32::MAX,
+            "padding sorts last"
+        );
+    }
+
+    #[test]
+    fn test_random_f32_keys_are_in_range() {
+        let keys = random_keys(1000, KeyType::F32, 9);
+        assert!(keys
+            .iter()
+            .map(|&bits| f32::from_bits(bits))
+            .all(|key| (-1000.0..=1000.0).contains(&key)));
+    }
+
+    #[test]
+    fn test_snapshot_rgba_size() {
+        let snapshots = vec![vec![3, 1, 2], vec![1, 2, 3]];
+        let rgba = snapshot_rgba(&snapshots, KeyType::U32);
+        assert_eq!(rgba.len(), 6 * 4);
+        // The smallest key is blue, the largest red
+        assert_eq!(&rgba[4..8], &[0, 40, 255, 255]);
+        assert_eq!(&rgba[0..4], &[255, 220, 0, 255]);
+    }
+}
@@ -0,0 +1,448 @@
+//! Blend state truth table: what a [`BlendState`] does to pairs of colors
+//!
+//! The blend equation is evaluated on the CPU exactly as the WebGPU spec
+//! describes it, `src × srcFactor op dst × dstFactor` per channel with the
+//! result clamped for a unorm target. [`BlendVisualizer`] lays a set of
+//! source swatches over a set of destination swatches and shows the output
+//! of every pair next to the formula, so each factor and operation choice in
+//! the pipeline panel can be seen at work.
+
+use crate::render_pipeline::{BlendComponent, BlendFactor, BlendOperation, BlendState};
+use crate::theme::{self, BackdropPattern};
+
+/// Colors drawn by the fragment shader, as straight (not premultiplied) RGBA
+pub const SOURCE_SWATCHES: &[(&str, [f32; 4])] = &[
+    ("Red", [1.0, 0.0, 0.0, 1.0]),
+    ("Green 50%", [0.0, 1.0, 0.0, 0.5]),
+    ("Blue 25%", [0.0, 0.0, 1.0, 0.25]),
+    ("White 50%", [1.0, 1.0, 1.0, 0.5]),
+    ("Clear", [0.0, 0.0, 0.0, 0.0]),
+];
+
+/// Colors already in the render target
+pub const DESTINATION_SWATCHES: &[(&str, [f32; 4])] = &[
+    ("Black", [0.0, 0.0, 0.0, 1.0]),
+    ("White", [1.0, 1.0, 1.0, 1.0]),
+    ("Yellow", [1.0, 1.0, 0.0, 1.0]),
+    ("Gray 50%", [0.5, 0.5, 0.5, 0.5]),
+    ("Transparent", [0.0, 0.0, 0.0, 0.0]),
+];
+
+/// RGBA value of a blend factor for a source, destination and blend constant
+pub fn factor_value(
+    factor: BlendFactor,
+    src: [f32; 4],
+    dst: [f32; 4],
+    constant: [f32; 4],
+) -> [f32; 4] {
+    let one_minus = |v: [f32; 4]| v.map(|c| 1.0 - c);
+    match factor {
+        BlendFactor::Zero => [0.0; 4],
+        BlendFactor::One => [1.0; 4],
+        BlendFactor::Src => src,
+        BlendFactor::OneMinusSrc => one_minus(src),
+        BlendFactor::SrcAlpha => [src[3]; 4],
+        BlendFactor::OneMinusSrcAlpha => [1.0 - src[3]; 4],
+        BlendFactor::Dst => dst,
+        BlendFactor::OneMinusDst => one_minus(dst),
+        BlendFactor::DstAlpha => [dst[3]; 4],
+        BlendFactor::OneMinusDstAlpha => [1.0 - dst[3]; 4],
+        BlendFactor::Constant => constant,
+        BlendFactor::OneMinusConstant => one_minus(constant),
+        BlendFactor::SrcAlphaSaturated => {
+            let f = src[3].min(1.0 - dst[3]);
+            [f, f, f, 1.0]
+        }
+    }
+}
+
+/// Short name of a factor as it appears in a formula
+pub fn factor_symbol(factor: BlendFactor) -> &'static str {
+    match factor {
+        BlendFactor::Zero => "0",
+        BlendFactor::One => "1",
+        BlendFactor::Src => "src",
+        BlendFactor::OneMinusSrc => "(1 − src)",
+        BlendFactor::SrcAlpha => "src.a",
+        BlendFactor::OneMinusSrcAlpha => "(1 − src.a)",
+        BlendFactor::Dst => "dst",
+        BlendFactor::OneMinusDst => "(1 − dst)",
+        BlendFactor::DstAlpha => "dst.a",
+        BlendFactor::OneMinusDstAlpha => "(1 − dst.a)",
+        BlendFactor::Constant => "const",
+        BlendFactor::OneMinusConstant => "(1 − const)",
+        BlendFactor::SrcAlphaSaturated => "min(src.a, 1 − dst.a)",
+    }
+}
+
+/// The equation a blend component evaluates, e.g. `src × src.a + dst × (1 − src.a)`
+pub fn formula(component: &BlendComponent) -> String {
+    let term = |name: &str, factor: BlendFactor| match factor {
+        BlendFactor::One => name.to_string(),
+        _ => format!("{} × {}", name, factor_symbol(factor)),
+    };
+    let src = term("src", component.src_factor);
+    let dst = term("dst", component.dst_factor);
+    match component.operation {
+        BlendOperation::Add => format!("{} + {}", src, dst),
+        BlendOperation::Subtract => format!("{} − {}", src, dst),
+        BlendOperation::ReverseSubtract => format!("{} − {}", dst, src),
+        BlendOperation::Min => "min(src, dst)".to_string(),
+        BlendOperation::Max => "max(src, dst)".to_string(),
+    }
+}
+
+/// Whether the component uses the blend constant set on the render pass
+pub fn uses_constant(component: &BlendComponent) -> bool {
+    [component.src_factor, component.dst_factor]
+        .iter()
+        .any(|f| matches!(f, BlendFactor::Constant | BlendFactor::OneMinusConstant))
+}
+
+/// Min and max ignore the factors; WebGPU requires both to be One with them
+pub fn ignored_factors(component: &BlendComponent) -> bool {
+    matches!(
+        component.operation,
+        BlendOperation::Min | BlendOperation::Max
+    ) && (component.src_factor != BlendFactor::One || component.dst_factor != BlendFactor::One)
+}
+
+/// The terms of one channel of the blend equation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelBlend {
+    pub src: f32,
+    pub src_factor: f32,
+    pub dst: f32,
+    pub dst_factor: f32,
+    pub operation: BlendOperation,
+    /// Output as stored in a unorm target
+    pub result: f32,
+}
+
+impl ChannelBlend {
+    fn new(
+        src: f32,
+        src_factor: f32,
+        dst: f32,
+        dst_factor: f32,
+        operation: BlendOperation,
+    ) -> Self {
+        let (s, d) = (src * src_factor, dst * dst_factor);
+        let result = match operation {
+            BlendOperation::Add => s + d,
+            BlendOperation::Subtract => s - d,
+            BlendOperation::ReverseSubtract => d - s,
+            BlendOperation::Min => src.min(dst),
+            BlendOperation::Max => src.max(dst),
+        };
+        Self {
+            src,
+            src_factor,
+            dst,
+            dst_factor,
+            operation,
+            result: result.clamp(0.0, 1.0),
+        }
+    }
+
+    /// The equation with numbers filled in, e.g. `1.00 × 0.50 + 0.00 × 0.50 = 0.50`
+    pub fn equation(&self) -> String {
+        let src = format!("{:.2} × {:.2}", self.src, self.src_factor);
+        let dst = format!("{:.2} × {:.2}", self.dst, self.dst_factor);
+        let expression = match self.operation {
+            BlendOperation::Add => format!("{} + {}", src, dst),
+            BlendOperation::Subtract => format!("{} − {}", src, dst),
+            BlendOperation::ReverseSubtract => format!("{} − {}", dst, src),
+            BlendOperation::Min => format!("min({:.2}, {:.2})", self.src, self.dst),
+            BlendOperation::Max => format!("max({:.2}, {:.2})", self.src, self.dst),
+        };
+        format!("{} = {:.2}", expression, self.result)
+    }
+}
+
+/// Every channel of blending `src` over `dst`
+///
+/// RGB use the color component and the RGB of its factors, alpha uses the
+/// alpha component and the alpha of its factors.
+pub fn blend_channels(
+    state: &BlendState,
+    src: [f32; 4],
+    dst: [f32; 4],
+    constant: [f32; 4],
+) -> [ChannelBlend; 4] {
+    std::array::from_fn(|i| {
+        let component = if i == 3 { &state.alpha } else { &state.color };
+        ChannelBlend::new(
+            src[i],
+            factor_value(component.src_factor, src, dst, constant)[i],
+            dst[i],
+            factor_value(component.dst_factor, src, dst, constant)[i],
+            component.operation,
+        )
+    })
+}
+
+/// Color stored after blending `src` over `dst`
+pub fn blend_color(
+    state: &BlendState,
+    src: [f32; 4],
+    dst: [f32; 4],
+    constant: [f32; 4],
+) -> [f32; 4] {
+    blend_channels(state, src, dst, constant).map(|channel| channel.result)
+}
+
+/// Output for every destination (rows) and source (columns) swatch
+pub fn truth_table(state: &BlendState, constant: [f32; 4]) -> Vec<Vec<[f32; 4]>> {
+    DESTINATION_SWATCHES
+        .iter()
+        .map(|(_, dst)| {
+            SOURCE_SWATCHES
+                .iter()
+                .map(|(_, src)| blend_color(state, *src, *dst, constant))
+                .collect()
+        })
+        .collect()
+}
+
+fn color32(rgba: [f32; 4]) -> egui::Color32 {
+    let [r, g, b, a] = rgba.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    egui::Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+/// Grid of source swatches blended over destination swatches
+pub struct BlendVisualizer {
+    /// Blend constant used by the Constant factors
+    pub constant: [f32; 4],
+    /// Pattern drawn behind the swatches so alpha shows, in the theme's
+    /// backdrop colors
+    pub backdrop: BackdropPattern,
+}
+
+impl Default for BlendVisualizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlendVisualizer {
+    pub fn new() -> Self {
+        Self {
+            constant: [0.5, 0.5, 0.5, 0.5],
+            backdrop: theme::current().backdrop,
+        }
+    }
+
+    /// Show the formulas and the truth table of `state`
+    pub fn ui(&mut self, ui: &mut egui::Ui, state: &BlendState) {
+        egui::Grid::new("blend_visualizer_formulas")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Color:");
+                ui.monospace(format!("rgb = {}", formula(&state.color)));
+                ui.end_row();
+                ui.label("Alpha:");
+                ui.monospace(format!("a = {}", formula(&state.alpha)));
+                ui.end_row();
+            });
+        if ignored_factors(&state.color) || ignored_factors(&state.alpha) {
+            ui.colored_label(
+                egui::Color32::from_rgb(230, 180, 60),
+                "⚠ Min and Max ignore the factors; WebGPU rejects factors other than One with them",
+            );
+        }
+        ui.horizontal(|ui| {
+            if uses_constant(&state.color) || uses_constant(&state.alpha) {
+                ui.label("Blend constant:");
+                ui.color_edit_button_rgba_unmultiplied(&mut self.constant);
+            }
+            ui.label("Backdrop:");
+            theme::backdrop_selector(ui, "blend_visualizer_backdrop", &mut self.backdrop);
+        });
+        ui.add_space(5.0);
+
+        let cell = 56.0;
+        let table = truth_table(state, self.constant);
+        egui::Grid::new("blend_visualizer_table")
+            .spacing([4.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("dst \\ src");
+                for (name, color) in SOURCE_SWATCHES {
+                    self.swatch_ui(ui, egui::vec2(cell, cell * 0.5), *color)
+                        .on_hover_text(*name);
+                }
+                ui.end_row();
+
+                for ((dst_name, dst), row) in DESTINATION_SWATCHES.iter().zip(&table) {
+                    self.swatch_ui(ui, egui::vec2(cell, cell), *dst)
+                        .on_hover_text(*dst_name);
+                    for ((src_name, src), output) in SOURCE_SWATCHES.iter().zip(row) {
+                        self.cell_ui(ui, cell, *src, *dst, *output)
+                            .on_hover_ui(|ui| {
+                                ui.strong(format!("{} over {}", src_name, dst_name));
+                                let channels = blend_channels(state, *src, *dst, self.constant);
+                                for (name, channel) in ["R", "G", "B", "A"].iter().zip(channels) {
+                                    ui.monospace(format!("{}: {}", name, channel.equation()));
+                                }
+                            });
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.label(
+            egui::RichText::new(
+                "Each cell shows the destination on the left, the source on the right and \
+                the blended output where they overlap. Hover a cell for the numbers.",
+            )
+            .weak(),
+        );
+    }
+
+    fn paint_background(&self, painter: &egui::Painter, rect: egui::Rect) {
+        theme::current().paint_backdrop_pattern(painter, rect, self.backdrop);
+    }
+
+    fn swatch_ui(&self, ui: &mut egui::Ui, size: egui::Vec2, color: [f32; 4]) -> egui::Response {
+        let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+        self.paint_background(&painter, response.rect);
+        painter.rect_filled(response.rect, 0.0, color32(color));
+        response
+    }
+
+    /// Destination and source side by side with the output where they overlap
+    fn cell_ui(
+        &self,
+        ui: &mut egui::Ui,
+        size: f32,
+        src: [f32; 4],
+        dst: [f32; 4],
+        output: [f32; 4],
+    ) -> egui::Response {
+        let (response, painter) = ui.allocate_painter(egui::vec2(size, size), egui::Sense::hover());
+        let rect = response.rect;
+        self.paint_background(&painter, rect);
+        let third = rect.width() / 3.0;
+        let dst_rect =
+            egui::Rect::from_min_max(rect.min, egui::pos2(rect.min.x + third, rect.max.y));
+        let src_rect =
+            egui::Rect::from_min_max(egui::pos2(rect.max.x - third, rect.min.y), rect.max);
+        let overlap = egui::Rect::from_min_max(dst_rect.right_top(), src_rect.left_bottom());
+        painter.rect_filled(dst_rect, 0.0, color32(dst));
+        painter.rect_filled(src_rect, 0.0, color32(src));
+        painter.rect_filled(overlap, 0.0, color32(output));
+        painter.rect_stroke(
+            overlap,
+            0.0,
+            egui::Stroke::new(1.0, egui::Color32::from_gray(200)),
+            egui::StrokeKind::Inside,
+        );
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(src: BlendFactor, dst: BlendFactor, op: BlendOperation) -> BlendComponent {
+        BlendComponent::new(src, dst, op)
+    }
+
+    #[test]
+    fn test_replace_outputs_source() {
+        let state = BlendState::replace();
+        for (_, src) in SOURCE_SWATCHES {
+            for (_, dst) in DESTINATION_SWATCHES {
+                assert_eq!(blend_color(&state, *src, *dst, [0.0; 4]), *src);
+            }
+        }
+    }
+
+    #[test]
+    fn test_straight_alpha_over() {
+        let over = component(
+            BlendFactor::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha,
+            BlendOperation::Add,
+        );
+        let state = BlendState::new(over, over);
+        let out = blend_color(&state, [0.0, 1.0, 0.0, 0.5], [1.0, 1.0, 1.0, 1.0], [0.0; 4]);
+        assert_eq!(out, [0.5, 1.0, 0.5, 0.75]);
+    }
+
+    #[test]
+    fn test_subtract_clamps_and_min_ignores_factors() {
+        let state = BlendState::new(
+            component(
+                BlendFactor::One,
+                BlendFactor::One,
+                BlendOperation::ReverseSubtract,
+            ),
+            component(BlendFactor::Zero, BlendFactor::Zero, BlendOperation::Min),
+        );
+        let out = blend_color(
+            &state,
+            [1.0, 0.25, 0.0, 0.3],
+            [0.5, 0.5, 0.5, 0.6],
+            [0.0; 4],
+        );
+        assert_eq!(out, [0.0, 0.25, 0.5, 0.3]);
+        assert!(ignored_factors(&state.alpha));
+        assert!(!ignored_factors(&state.color));
+    }
+
+    #[test]
+    fn test_constant_and_saturated_factors() {
+        let state = BlendState::new(
+            component(
+                BlendFactor::SrcAlphaSaturated,
+                BlendFactor::Constant,
+                BlendOperation::Add,
+            ),
+            component(
+                BlendFactor::SrcAlphaSaturated,
+                BlendFactor::OneMinusConstant,
+                BlendOperation::Add,
+            ),
+        );
+        assert!(uses_constant(&state.color));
+        let channels = blend_channels(&state, [1.0, 1.0, 1.0, 0.8], [0.5, 0.5, 0.5, 0.5], [0.5; 4]);
+        // min(0.8, 1 - 0.5) for RGB, 1 for alpha
+        assert_eq!(channels[0].src_factor, 0.5);
+        assert_eq!(channels[3].src_factor, 1.0);
+        assert_eq!(channels[0].result, 0.75);
+        assert_eq!(channels[3].equation(), "0.80 × 1.00 + 0.50 × 0.50 = 1.00");
+    }
+
+    #[test]
+    fn test_formula_text() {
+        let over = component(
+            BlendFactor::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha,
+            BlendOperation::Add,
+        );
+        assert_eq!(formula(&over), "src × src.a + dst × (1 − src.a)");
+        let additive = component(BlendFactor::One, BlendFactor::One, BlendOperation::Add);
+        assert_eq!(formula(&additive), "src + dst");
+        let reverse = component(
+            BlendFactor::One,
+            BlendFactor::Dst,
+            BlendOperation::ReverseSubtract,
+        );
+        assert_eq!(formula(&reverse), "dst × dst − src");
+        let max = component(BlendFactor::One, BlendFactor::One, BlendOperation::Max);
+        assert_eq!(formula(&max), "max(src, dst)");
+    }
+
+    #[test]
+    fn test_truth_table_shape() {
+        let table = truth_table(&BlendState::alpha_blending(), [0.0; 4]);
+        assert_eq!(table.len(), DESTINATION_SWATCHES.len());
+        assert!(table.iter().all(|row| row.len() == SOURCE_SWATCHES.len()));
+        // A clear premultiplied source leaves the destination alone
+        let clear = SOURCE_SWATCHES.len() - 1;
+        for (row, (_, dst)) in table.iter().zip(DESTINATION_SWATCHES) {
+            assert_eq!(row[clear], *dst);
+        }
+    }
+}
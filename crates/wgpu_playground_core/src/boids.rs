@@ -0,0 +1,707 @@
+//! Boids flocking simulation
+//!
+//! Every boid steers by three rules over the neighbours it can see:
+//! cohesion towards their center, alignment with their average velocity and
+//! separation from the ones that come too close. A compute pass applies the
+//! rules to every boid, reading last frame's state from one storage buffer
+//! and writing the new state to the other; the buffers swap roles every
+//! frame, so no boid sees a neighbour that has already moved. The render
+//! pass then draws one triangle per boid, instanced from the buffer just
+//! written, bound as a vertex buffer with an instance step mode.
+//!
+//! Circular obstacles push boids away as they approach. The world is
+//! `2 * aspect` wide and 2 high, centered on the origin, and boids leaving
+//! one edge come back on the opposite one.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::upload_belt;
+use wgpu::util::DeviceExt;
+
+/// Largest number of boids; the state buffers are allocated for this many
+pub const MAX_BOIDS: u32 = 16_384;
+
+/// Boids when the example starts
+pub const DEFAULT_BOIDS: u32 = 2_048;
+
+/// Obstacles that fit the params uniform; adding more replaces the oldest
+pub const MAX_OBSTACLES: usize = 16;
+
+/// Radius of an obstacle in world units
+pub const OBSTACLE_RADIUS: f32 = 0.08;
+
+const WORKGROUP_SIZE: u32 = 64;
+
+pub const BOIDS_COMPUTE_SHADER: &str = r#"// Boids Example
+// One invocation per boid reads every boid from `boids_in` and writes its
+// own new state to `boids_out`; the host swaps the buffers every frame.
+
+struct Boid {
+    position: vec2<f32>,
+    velocity: vec2<f32>,
+}
+
+struct Params {
+    delta_time: f32,
+    boid_count: u32,
+    obstacle_count: u32,
+    aspect: f32,
+    cohesion: f32,
+    separation: f32,
+    alignment: f32,
+    visual_range: f32,
+    separation_distance: f32,
+    max_speed: f32,
+    // xy center, z radius
+    obstacles: array<vec4<f32>, 16>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> boids_in: array<Boid>;
+@group(0) @binding(2) var<storage, read_write> boids_out: array<Boid>;
+
+// The shortest offset between two points of the wrapping world
+fn wrap_offset(offset: vec2<f32>) -> vec2<f32> {
+    let extent = vec2<f32>(2.0 * params.aspect, 2.0);
+    return offset - round(offset / extent) * extent;
+}
+
+@compute @workgroup_size(64)
+fn simulate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= params.boid_count {
+        return;
+    }
+    let boid = boids_in[index];
+
+    var center = vec2<f32>(0.0);
+    var heading = vec2<f32>(0.0);
+    var away = vec2<f32>(0.0);
+    var neighbours = 0u;
+    for (var i = 0u; i < params.boid_count; i++) {
+        if i == index {
+            continue;
+        }
+        let other = boids_in[i];
+        let offset = wrap_offset(other.position - boid.position);
+        let distance = length(offset);
+        if distance < params.visual_range {
+            center += offset;
+            heading += other.velocity;
+            neighbours++;
+        }
+        if distance > 0.0 && distance < params.separation_distance {
+            away -= offset / distance * (1.0 - distance / params.separation_distance);
+        }
+    }
+
+    var steer = away * params.separation;
+    if neighbours > 0u {
+        let count = f32(neighbours);
+        steer += center / count * params.cohesion;
+        steer += (heading / count - boid.velocity) * params.alignment;
+    }
+
+    // Obstacles push harder the deeper a boid gets into their margin
+    for (var i = 0u; i < params.obstacle_count; i++) {
+        let obstacle = params.obstacles[i];
+        let offset = boid.position - obstacle.xy;
+        let distance = length(offset);
+        let margin = obstacle.z + params.visual_range;
+        if distance > 0.0 && distance < margin {
+            steer += offset / distance * (margin - distance) / params.visual_range * 8.0;
+        }
+    }
+
+    var velocity = boid.velocity + steer * params.delta_time;
+    let speed = length(velocity);
+    let min_speed = params.max_speed * 0.25;
+    if speed > params.max_speed {
+        velocity *= params.max_speed / speed;
+    } else if speed < min_speed {
+        velocity = select(vec2<f32>(min_speed, 0.0), velocity / speed * min_speed, speed > 0.0);
+    }
+
+    // Wrap into [-aspect, aspect] x [-1, 1]
+    let extent = vec2<f32>(2.0 * params.aspect, 2.0);
+    let position = boid.position + velocity * params.delta_time;
+    boids_out[index] = Boid(position - floor(position / extent + 0.5) * extent, velocity);
+}
+"#;
+
+pub const BOIDS_RENDER_SHADER: &str = r#"// Boids rendering: one triangle per boid pointing along its velocity,
+// and a disc per obstacle
+
+struct Params {
+    delta_time: f32,
+    boid_count: u32,
+    obstacle_count: u32,
+    aspect: f32,
+    cohesion: f32,
+    separation: f32,
+    alignment: f32,
+    visual_range: f32,
+    separation_distance: f32,
+    max_speed: f32,
+    obstacles: array<vec4<f32>, 16>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+    @location(1) local: vec2<f32>,
+}
+
+fn to_clip(world: vec2<f32>) -> vec4<f32> {
+    return vec4<f32>(world.x / params.aspect, world.y, 0.0, 1.0);
+}
+
+@vertex
+fn vs_boid(
+    @builtin(vertex_index) vertex: u32,
+    @location(0) position: vec2<f32>,
+    @location(1) velocity: vec2<f32>,
+) -> VertexOutput {
+    var shape = array<vec2<f32>, 3>(
+        vec2<f32>(0.02, 0.0),
+        vec2<f32>(-0.012, 0.008),
+        vec2<f32>(-0.012, -0.008),
+    );
+    let forward = normalize(velocity + vec2<f32>(1e-6, 0.0));
+    let side = vec2<f32>(-forward.y, forward.x);
+    let corner = shape[vertex];
+
+    // Color by heading
+    let angle = atan2(forward.y, forward.x);
+    let color = 0.55 + 0.45 * cos(angle + vec3<f32>(0.0, 2.094, 4.189));
+
+    var out: VertexOutput;
+    out.position = to_clip(position + forward * corner.x + side * corner.y);
+    out.color = color;
+    out.local = vec2<f32>(0.0);
+    return out;
+}
+
+@fragment
+fn fs_boid(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+
+@vertex
+fn vs_obstacle(
+    @builtin(vertex_index) vertex: u32,
+    @builtin(instance_index) instance: u32,
+) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    let obstacle = params.obstacles[instance];
+    let local = corners[vertex];
+
+    var out: VertexOutput;
+    out.position = to_clip(obstacle.xy + local * obstacle.z);
+    out.color = vec3<f32>(0.85, 0.3, 0.25);
+    out.local = local;
+    return out;
+}
+
+@fragment
+fn fs_obstacle(in: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = length(in.local);
+    if distance > 1.0 {
+        discard;
+    }
+    // Darker rim
+    return vec4<f32>(in.color * select(1.0, 0.6, distance > 0.85), 1.0);
+}
+"#;
+
+/// The state of one boid, laid out like the shaders' `Boid`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Boid {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+}
+
+/// Weights and distances of the flocking rules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlockParams {
+    /// Steering towards the center of the visible neighbours
+    pub cohesion: f32,
+    /// Steering away from neighbours closer than `separation_distance`
+    pub separation: f32,
+    /// Steering towards the average velocity of the visible neighbours
+    pub alignment: f32,
+    /// How far a boid sees its neighbours
+    pub visual_range: f32,
+    pub separation_distance: f32,
+    pub max_speed: f32,
+}
+
+impl Default for FlockParams {
+    fn default() -> Self {
+        Self {
+            cohesion: 2.0,
+            separation: 3.0,
+            alignment: 1.5,
+            visual_range: 0.15,
+            separation_distance: 0.04,
+            max_speed: 0.5,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    delta_time: f32,
+    boid_count: u32,
+    obstacle_count: u32,
+    aspect: f32,
+    cohesion: f32,
+    separation: f32,
+    alignment: f32,
+    visual_range: f32,
+    separation_distance: f32,
+    max_speed: f32,
+    _padding: [f32; 2],
+    obstacles: [[f32; 4]; MAX_OBSTACLES],
+}
+
+/// Boids scattered over the world with random headings, the same for the
+/// same `seed`
+pub fn initial_boids(count: u32, aspect: f32, seed: u32) -> Vec<Boid> {
+    // xorshift32, which never leaves zero
+    let mut state = seed.max(1);
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as f32 / u32::MAX as f32
+    };
+    (0..count)
+        .map(|_| {
+            let position = [(random() * 2.0 - 1.0) * aspect, random() * 2.0 - 1.0];
+            let angle = random() * std::f32::consts::TAU;
+            let speed = 0.1 + random() * 0.2;
+            Boid {
+                position,
+                velocity: [angle.cos() * speed, angle.sin() * speed],
+            }
+        })
+        .collect()
+}
+
+/// The world position under a point of the preview, given as a fraction of
+/// its width and height from the top left
+pub fn canvas_to_world(uv: [f32; 2], aspect: f32) -> [f32; 2] {
+    [(uv[0] * 2.0 - 1.0) * aspect, 1.0 - uv[1] * 2.0]
+}
+
+/// Simulation and renderer of the boids example
+pub struct Flock {
+    simulate_pipeline: wgpu::ComputePipeline,
+    boid_pipeline: wgpu::RenderPipeline,
+    obstacle_pipeline: wgpu::RenderPipeline,
+    /// Boid states, read and written alternately
+    boid_buffers: [wgpu::Buffer; 2],
+    /// Reading buffer `i` and writing the other one
+    simulate_bind_groups: [wgpu::BindGroup; 2],
+    render_bind_group: wgpu::BindGroup,
+    params_buffer: wgpu::Buffer,
+    /// Index of the buffer holding the latest state
+    current: usize,
+    boid_count: u32,
+    aspect: f32,
+    obstacles: Vec<[f32; 2]>,
+    /// Seed of the last [`initial_boids`]
+    seed: u32,
+    pub params: FlockParams,
+    pub paused: bool,
+}
+
+impl Flock {
+    /// Create the flock drawing into `color_format` targets with the given
+    /// width to height ratio
+    pub fn new(device: &wgpu::Device, color_format: wgpu::TextureFormat, aspect: f32) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let compute_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Boids Compute Shader"),
+            source: wgpu::ShaderSource::Wgsl(BOIDS_COMPUTE_SHADER.into()),
+        });
+        let render_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Boids Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(BOIDS_RENDER_SHADER.into()),
+        });
+
+        let seed = 1;
+        let boids = initial_boids(MAX_BOIDS, aspect, seed);
+        tracker.record(ApiCategory::Buffer, "create_buffer_init");
+        let boid_buffers = [0, 1].map(|index| {
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(if index == 0 {
+                    "Boids Buffer A"
+                } else {
+                    "Boids Buffer B"
+                }),
+                contents: bytemuck::cast_slice(&boids),
+                usage: wgpu::BufferUsages::STORAGE
+                    | wgpu::BufferUsages::VERTEX
+                    | wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST,
+            })
+        });
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Boids Params"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let params_entry = |visibility| wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let storage_entry = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<Boid>() as u64),
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let simulate_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Boids Simulate Bind Group Layout"),
+            entries: &[
+                params_entry(wgpu::ShaderStages::COMPUTE),
+                storage_entry(1, true),
+                storage_entry(2, false),
+            ],
+        });
+        let render_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Boids Render Bind Group Layout"),
+            entries: &[params_entry(
+                wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+            )],
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let simulate_bind_groups = [0, 1].map(|read| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Boids Simulate Bind Group"),
+                layout: &simulate_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: boid_buffers[read].as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: boid_buffers[1 - read].as_entire_binding(),
+                    },
+                ],
+            })
+        });
+        let render_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Boids Render Bind Group"),
+            layout: &render_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: params_buffer.as_entire_binding(),
+            }],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let simulate_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Boids Simulate Pipeline Layout"),
+                bind_group_layouts: &[Some(&simulate_layout)],
+                immediate_size: 0,
+            });
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Boids Render Pipeline Layout"),
+                bind_group_layouts: &[Some(&render_layout)],
+                immediate_size: 0,
+            });
+
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let simulate_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Boids Simulate Pipeline"),
+            layout: Some(&simulate_pipeline_layout),
+            module: &compute_shader,
+            entry_point: Some("simulate"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        // The boid state doubles as per-instance vertex data
+        let boid_attributes = wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2];
+        let boid_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Boid>() as u64,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &boid_attributes,
+        };
+        let render_pipeline = |label, entry_points: [&str; 2], buffers| {
+            tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&render_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &render_shader,
+                    entry_point: Some(entry_points[0]),
+                    buffers,
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &render_shader,
+                    entry_point: Some(entry_points[1]),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+        let boid_pipeline = render_pipeline(
+            "Boids Pipeline",
+            ["vs_boid", "fs_boid"],
+            std::slice::from_ref(&boid_layout),
+        );
+        let obstacle_pipeline = render_pipeline(
+            "Boids Obstacle Pipeline",
+            ["vs_obstacle", "fs_obstacle"],
+            &[],
+        );
+
+        Self {
+            simulate_pipeline,
+            boid_pipeline,
+            obstacle_pipeline,
+            boid_buffers,
+            simulate_bind_groups,
+            render_bind_group,
+            params_buffer,
+            current: 0,
+            boid_count: DEFAULT_BOIDS,
+            aspect,
+            obstacles: Vec::new(),
+            seed,
+            params: FlockParams::default(),
+            paused: false,
+        }
+    }
+
+    /// Grow or shrink the flock, clamped to [`MAX_BOIDS`]
+    pub fn set_boid_count(&mut self, count: u32) {
+        self.boid_count = count.clamp(1, MAX_BOIDS);
+    }
+
+    pub fn boid_count(&self) -> u32 {
+        self.boid_count
+    }
+
+    /// Neighbour checks per frame, every boid against every other
+    pub fn neighbour_checks(&self) -> u64 {
+        self.boid_count as u64 * self.boid_count.saturating_sub(1) as u64
+    }
+
+    /// Place an obstacle, replacing the oldest once there are [`MAX_OBSTACLES`]
+    pub fn add_obstacle(&mut self, position: [f32; 2]) {
+        if self.obstacles.len() == MAX_OBSTACLES {
+            self.obstacles.remove(0);
+        }
+        self.obstacles.push(position);
+    }
+
+    pub fn clear_obstacles(&mut self) {
+        self.obstacles.clear();
+    }
+
+    pub fn obstacles(&self) -> &[[f32; 2]] {
+        &self.obstacles
+    }
+
+    /// World aspect ratio of the last [`Flock::update`]
+    pub fn aspect(&self) -> f32 {
+        self.aspect
+    }
+
+    /// Scatter the boids again, differently every time
+    pub fn reset(&mut self, queue: &wgpu::Queue) {
+        self.seed += 1;
+        let boids = initial_boids(MAX_BOIDS, self.aspect, self.seed);
+        upload_belt::write_buffer(
+            queue,
+            &self.boid_buffers[self.current],
+            0,
+            bytemuck::cast_slice(&boids),
+        );
+    }
+
+    /// Upload the rules, the obstacles and the time step of the next frame
+    pub fn update(&mut self, queue: &wgpu::Queue, aspect: f32, delta_time: f32) {
+        self.aspect = aspect;
+        let mut obstacles = [[0.0; 4]; MAX_OBSTACLES];
+        for (slot, [x, y]) in obstacles.iter_mut().zip(&self.obstacles) {
+            *slot = [*x, *y, OBSTACLE_RADIUS, 0.0];
+        }
+        let params = Params {
+            delta_time: if self.paused { 0.0 } else { delta_time },
+            boid_count: self.boid_count,
+            obstacle_count: self.obstacles.len() as u32,
+            aspect,
+            cohesion: self.params.cohesion,
+            separation: self.params.separation,
+            alignment: self.params.alignment,
+            visual_range: self.params.visual_range,
+            separation_distance: self.params.separation_distance,
+            max_speed: self.params.max_speed,
+            _padding: [0.0; 2],
+            obstacles,
+        };
+        upload_belt::write_buffer(queue, &self.params_buffer, 0, bytemuck::bytes_of(&params));
+    }
+
+    /// Step the simulation and draw the flock into `target`
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        clear_color: wgpu::Color,
+    ) {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Boids Encoder"),
+        });
+        if !self.paused {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Boids Simulate Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.simulate_pipeline);
+            compute_pass.set_bind_group(0, &self.simulate_bind_groups[self.current], &[]);
+            tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+            compute_pass.dispatch_workgroups(self.boid_count.div_ceil(WORKGROUP_SIZE), 1, 1);
+            self.current = 1 - self.current;
+        }
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Boids Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+            render_pass.set_pipeline(&self.obstacle_pipeline);
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..6, 0..self.obstacles.len() as u32);
+
+            render_pass.set_pipeline(&self.boid_pipeline);
+            tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+            render_pass.set_vertex_buffer(0, self.boid_buffers[self.current].slice(..));
+            // Three vertices per boid, the instance step reads one boid each
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..3, 0..self.boid_count);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+
+    /// The buffer holding the latest boid states
+    pub fn boids_buffer(&self) -> &wgpu::Buffer {
+        &self.boid_buffers[self.current]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        validate(BOIDS_COMPUTE_SHADER);
+        validate(BOIDS_RENDER_SHADER);
+        let obstacles = format!("obstacles: array<vec4<f32>, {}>", MAX_OBSTACLES);
+        assert!(BOIDS_COMPUTE_SHADER.contains(&obstacles));
+        assert!(BOIDS_RENDER_SHADER.contains(&obstacles));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<Boid>(), 16);
+        // Ten scalars padded to 48 bytes, then the obstacles
+        assert_eq!(std::mem::size_of::<Params>(), 48 + 16 * MAX_OBSTACLES);
+    }
+
+    #[test]
+    fn test_initial_boids_are_in_the_world() {
+        let aspect = 1.5;
+        let boids = initial_boids(1000, aspect, 7);
+        assert_eq!(boids.len(), 1000);
+        assert!(boids.iter().all(|boid| {
+            let [x, y] = boid.position;
+            x.abs() <= aspect && y.abs() <= 1.0
+        }));
+        assert_eq!(boids, initial_boids(1000, aspect, 7));
+    }
+
+    #[test]
+    fn test_canvas_to_world() {
+        assert_eq!(canvas_to_world([0.5, 0.5], 2.0), [0.0, 0.0]);
+        assert_eq!(canvas_to_world([0.0, 0.0], 2.0), [-2.0, 1.0]);
+        assert_eq!(canvas_to_world([1.0, 1.0], 2.0), [2.0, -1.0]);
+    }
+}
@@ -0,0 +1,715 @@
+//! False-color images of raw buffer contents
+//!
+//! Compute shaders often write 2D data into plain storage buffers:
+//! heightfields, histograms, simulation grids. [`BufferImage`] reinterprets
+//! such a buffer as a grid with a user-chosen number of elements per row and
+//! renders it through a small visualization pipeline into a texture egui can
+//! display. The buffer is uploaded as-is; decoding and color mapping happen
+//! in the fragment shader.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::upload_belt;
+
+/// Format of the target texture; egui samples it like any other image
+const IMAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// How the bytes of the buffer are split into grid cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementType {
+    Float32,
+    Uint32,
+    Sint32,
+    /// One byte per cell, e.g. masks or 8-bit histograms
+    Uint8,
+}
+
+impl ElementType {
+    /// Get a human-readable name for the element type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ElementType::Float32 => "f32",
+            ElementType::Uint32 => "u32",
+            ElementType::Sint32 => "i32",
+            ElementType::Uint8 => "u8",
+        }
+    }
+
+    /// Get all available element types
+    pub fn all() -> &'static [ElementType] {
+        &[
+            ElementType::Float32,
+            ElementType::Uint32,
+            ElementType::Sint32,
+            ElementType::Uint8,
+        ]
+    }
+
+    /// Size of one element in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            ElementType::Uint8 => 1,
+            _ => 4,
+        }
+    }
+
+    /// Value of element `index`, or `None` past the end of `data`
+    pub fn decode(&self, data: &[u8], index: usize) -> Option<f32> {
+        let start = index.checked_mul(self.size())?;
+        let bytes = data.get(start..start + self.size())?;
+        let word = || [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Some(match self {
+            ElementType::Float32 => f32::from_le_bytes(word()),
+            ElementType::Uint32 => u32::from_le_bytes(word()) as f32,
+            ElementType::Sint32 => i32::from_le_bytes(word()) as f32,
+            ElementType::Uint8 => f32::from(bytes[0]),
+        })
+    }
+
+    /// Identifier used by the visualization shader
+    fn shader_id(&self) -> u32 {
+        match self {
+            ElementType::Float32 => 0,
+            ElementType::Uint32 => 1,
+            ElementType::Sint32 => 2,
+            ElementType::Uint8 => 3,
+        }
+    }
+}
+
+/// Mapping from normalized values to colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    Grayscale,
+    /// Black through red and yellow to white
+    Heat,
+    /// Perceptually uniform blue-green-yellow
+    Viridis,
+    /// Blue below the middle of the range, red above; for signed data
+    Diverging,
+}
+
+impl Colormap {
+    /// Get a human-readable name for the colormap
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Colormap::Grayscale => "Grayscale",
+            Colormap::Heat => "Heat",
+            Colormap::Viridis => "Viridis",
+            Colormap::Diverging => "Diverging",
+        }
+    }
+
+    /// Get all available colormaps
+    pub fn all() -> &'static [Colormap] {
+        &[
+            Colormap::Grayscale,
+            Colormap::Heat,
+            Colormap::Viridis,
+            Colormap::Diverging,
+        ]
+    }
+
+    /// Identifier used by the visualization shader
+    fn shader_id(&self) -> u32 {
+        match self {
+            Colormap::Grayscale => 0,
+            Colormap::Heat => 1,
+            Colormap::Viridis => 2,
+            Colormap::Diverging => 3,
+        }
+    }
+}
+
+/// How a buffer is turned into an image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferImageSettings {
+    /// Elements per row
+    pub width: u32,
+    pub element_type: ElementType,
+    pub colormap: Colormap,
+    /// Values mapped to the ends of the colormap; `None` uses the data range
+    pub range: Option<(f32, f32)>,
+}
+
+impl Default for BufferImageSettings {
+    fn default() -> Self {
+        Self {
+            width: 64,
+            element_type: ElementType::Float32,
+            colormap: Colormap::Viridis,
+            range: None,
+        }
+    }
+}
+
+/// Size of the grid a buffer is shown as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridLayout {
+    pub width: u32,
+    pub height: u32,
+    /// Number of elements shown; the last row may be partly empty
+    pub elements: usize,
+    /// Elements left out because the grid hit the texture size limit
+    pub truncated: usize,
+}
+
+impl GridLayout {
+    /// Lay out `byte_len` bytes of `element_type` in rows of `width` elements
+    ///
+    /// At most `max_height` rows are shown. Returns `None` if there is not a
+    /// single element to show.
+    pub fn new(
+        byte_len: usize,
+        element_type: ElementType,
+        width: u32,
+        max_height: u32,
+    ) -> Option<Self> {
+        let total = byte_len / element_type.size();
+        if width == 0 || total == 0 || max_height == 0 {
+            return None;
+        }
+        let row = width as usize;
+        let rows = total.div_ceil(row).min(max_height as usize);
+        let elements = total.min(rows * row);
+        Some(Self {
+            width,
+            height: rows as u32,
+            elements,
+            truncated: total - elements,
+        })
+    }
+
+    /// Element index of cell (`x`, `y`), if it holds data
+    pub fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let index = y as usize * self.width as usize + x as usize;
+        (index < self.elements).then_some(index)
+    }
+}
+
+/// Smallest and largest finite value among the first `count` elements
+pub fn value_range(data: &[u8], element_type: ElementType, count: usize) -> Option<(f32, f32)> {
+    (0..count)
+        .map_while(|index| element_type.decode(data, index))
+        .filter(|value| value.is_finite())
+        .fold(None, |range, value| match range {
+            None => Some((value, value)),
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+        })
+}
+
+/// Uniforms of the visualization shader
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ImageParams {
+    width: u32,
+    height: u32,
+    count: u32,
+    element_type: u32,
+    colormap: u32,
+    min_value: f32,
+    max_value: f32,
+    _padding: u32,
+}
+
+const SHADER: &str = r#"
+struct Params {
+    width: u32,
+    height: u32,
+    count: u32,
+    element_type: u32,
+    colormap: u32,
+    min_value: f32,
+    max_value: f32,
+    _padding: u32,
+}
+
+@group(0) @binding(0) var<storage, read> data: array<u32>;
+@group(0) @binding(1) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // One triangle covering the whole target
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    var output: VertexOutput;
+    output.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    output.uv = uv;
+    return output;
+}
+
+fn load_value(index: u32) -> f32 {
+    var value: f32;
+    switch params.element_type {
+        case 0u: { value = bitcast<f32>(data[index]); }
+        case 1u: { value = f32(data[index]); }
+        case 2u: { value = f32(bitcast<i32>(data[index])); }
+        default: {
+            let word = data[index / 4u];
+            value = f32((word >> ((index % 4u) * 8u)) & 0xffu);
+        }
+    }
+    return value;
+}
+
+fn viridis(t: f32) -> vec3<f32> {
+    let c0 = vec3<f32>(0.2777273, 0.0054073, 0.3340998);
+    let c1 = vec3<f32>(0.1050930, 1.4046135, 1.3845902);
+    let c2 = vec3<f32>(-0.3308618, 0.2148476, 0.0950952);
+    let c3 = vec3<f32>(-4.6342305, -5.7991010, -19.3324410);
+    let c4 = vec3<f32>(6.2282699, 14.1799334, 56.6905526);
+    let c5 = vec3<f32>(4.7763850, -13.7451454, -65.3530326);
+    let c6 = vec3<f32>(-5.4354559, 4.6458526, 26.3124352);
+    return c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6)))));
+}
+
+fn colormap(t: f32) -> vec3<f32> {
+    var color: vec3<f32>;
+    switch params.colormap {
+        case 0u: { color = vec3<f32>(t); }
+        case 1u: { color = clamp(vec3<f32>(t * 3.0, t * 3.0 - 1.0, t * 3.0 - 2.0), vec3<f32>(0.0), vec3<f32>(1.0)); }
+        case 2u: { color = viridis(t); }
+        default: {
+            let blue = vec3<f32>(0.23, 0.30, 0.75);
+            let red = vec3<f32>(0.71, 0.02, 0.15);
+            color = select(mix(vec3<f32>(1.0), red, t * 2.0 - 1.0), mix(blue, vec3<f32>(1.0), t * 2.0), t < 0.5);
+        }
+    }
+    return clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let x = min(u32(input.uv.x * f32(params.width)), params.width - 1u);
+    let y = min(u32(input.uv.y * f32(params.height)), params.height - 1u);
+    let index = y * params.width + x;
+    if index >= params.count {
+        // Cells past the end of the buffer
+        return vec4<f32>(0.02, 0.02, 0.02, 1.0);
+    }
+
+    let value = load_value(index);
+    if value != value {
+        // NaN
+        return vec4<f32>(1.0, 0.0, 1.0, 1.0);
+    }
+    let span = params.max_value - params.min_value;
+    var t = 0.5;
+    if span > 0.0 {
+        t = clamp((value - params.min_value) / span, 0.0, 1.0);
+    }
+    // Colormaps are defined in sRGB; the target converts from linear
+    return vec4<f32>(pow(colormap(t), vec3<f32>(2.2)), 1.0);
+}
+"#;
+
+/// Visualization pipeline and target texture for one buffer image
+pub struct BufferImage {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params_buffer: wgpu::Buffer,
+    /// Uploaded copy of the buffer contents, reused while it is big enough
+    data_buffer: Option<wgpu::Buffer>,
+    texture: Option<wgpu::Texture>,
+    texture_view: Option<wgpu::TextureView>,
+    /// Texture ID for egui display
+    texture_id: Option<egui::TextureId>,
+    /// Whether `texture_id` refers to an older texture
+    texture_id_stale: bool,
+    layout: Option<GridLayout>,
+    /// Range the last image was mapped with
+    range: Option<(f32, f32)>,
+}
+
+impl BufferImage {
+    /// Create the visualization pipeline
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Buffer Image Shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Buffer Image Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Buffer Image Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Buffer Image Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: IMAGE_FORMAT,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Buffer Image Params"),
+            size: std::mem::size_of::<ImageParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            params_buffer,
+            data_buffer: None,
+            texture: None,
+            texture_view: None,
+            texture_id: None,
+            texture_id_stale: false,
+            layout: None,
+            range: None,
+        }
+    }
+
+    /// Render `data` as an image with the given settings
+    ///
+    /// Data beyond the device's storage binding size or texture size limits
+    /// is left out and reported in [`GridLayout::truncated`].
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        data: &[u8],
+        settings: &BufferImageSettings,
+    ) -> Result<GridLayout, String> {
+        let tracker = ApiCoverageTracker::global();
+        let limits = device.limits();
+
+        if settings.width > limits.max_texture_dimension_2d {
+            return Err(format!(
+                "Width {} exceeds the maximum texture dimension {}",
+                settings.width, limits.max_texture_dimension_2d
+            ));
+        }
+        let max_bytes = limits
+            .max_storage_buffer_binding_size
+            .min(limits.max_buffer_size) as usize
+            & !3;
+        let visible = &data[..data.len().min(max_bytes)];
+        let mut layout = GridLayout::new(
+            visible.len(),
+            settings.element_type,
+            settings.width,
+            limits.max_texture_dimension_2d,
+        )
+        .ok_or_else(|| "Not enough data for a single element".to_string())?;
+        layout.truncated += (data.len() - visible.len()) / settings.element_type.size();
+
+        let range = settings
+            .range
+            .or_else(|| value_range(visible, settings.element_type, layout.elements))
+            .unwrap_or((0.0, 1.0));
+
+        // Storage buffers hold whole words; pad the last one with zeroes
+        let used_bytes = layout.elements * settings.element_type.size();
+        let mut contents = visible[..used_bytes].to_vec();
+        contents.resize(used_bytes.next_multiple_of(4), 0);
+
+        let needs_buffer = self
+            .data_buffer
+            .as_ref()
+            .is_none_or(|buffer| buffer.size() < contents.len() as u64);
+        if needs_buffer {
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            self.data_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Buffer Image Data"),
+                size: contents.len() as u64,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        let data_buffer = self.data_buffer.as_ref().expect("data buffer was created");
+        upload_belt::write_buffer(queue, data_buffer, 0, &contents);
+
+        let params = ImageParams {
+            width: layout.width,
+            height: layout.height,
+            count: layout.elements as u32,
+            element_type: settings.element_type.shader_id(),
+            colormap: settings.colormap.shader_id(),
+            min_value: range.0,
+            max_value: range.1,
+            _padding: 0,
+        };
+        upload_belt::write_buffer(queue, &self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        self.ensure_texture(device, layout.width, layout.height);
+        let view = self.texture_view.as_ref().expect("texture was created");
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Buffer Image Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: data_buffer,
+                        offset: 0,
+                        size: wgpu::BufferSize::new(contents.len() as u64),
+                    }),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: self.params_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Buffer Image Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Buffer Image Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..3, 0..1);
+        }
+        upload_belt::submit(queue, Some(encoder.finish()));
+
+        self.layout = Some(layout);
+        self.range = Some(range);
+        Ok(layout)
+    }
+
+    /// Create the target texture unless one of the right size exists
+    fn ensure_texture(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        if self.texture.as_ref().is_some_and(|t| t.size() == size) {
+            return;
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Buffer Image Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: IMAGE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        tracker.record(ApiCategory::Texture, "create_view");
+        self.texture_view = Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        self.texture = Some(texture);
+        self.texture_id_stale = self.texture_id.is_some();
+    }
+
+    /// Texture holding the last rendered image, one texel per element
+    pub fn texture(&self) -> Option<&wgpu::Texture> {
+        self.texture.as_ref()
+    }
+
+    /// Grid of the last rendered image
+    pub fn layout(&self) -> Option<GridLayout> {
+        self.layout
+    }
+
+    /// Values the ends of the colormap stood for in the last image
+    pub fn range(&self) -> Option<(f32, f32)> {
+        self.range
+    }
+
+    /// Get or register texture ID for egui
+    ///
+    /// Elements are shown as sharp blocks, so the texture is sampled with
+    /// nearest filtering.
+    ///
+    /// Note: This method is only available when building for native targets.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_texture_id(
+        &mut self,
+        device: &wgpu::Device,
+        renderer: &mut egui_wgpu::Renderer,
+    ) -> Option<egui::TextureId> {
+        if self.texture_id_stale {
+            if let Some(id) = self.texture_id.take() {
+                renderer.free_texture(&id);
+            }
+            self.texture_id_stale = false;
+        }
+        if self.texture_id.is_none() {
+            if let Some(view) = &self.texture_view {
+                let id = renderer.register_native_texture(
+                    device,
+                    view,
+                    egui_wgpu::wgpu::FilterMode::Nearest,
+                );
+                self.texture_id = Some(id);
+            }
+        }
+        self.texture_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_element_decode() {
+        let data: Vec<u8> = [1.5f32, -2.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        assert_eq!(ElementType::Float32.decode(&data, 0), Some(1.5));
+        assert_eq!(ElementType::Float32.decode(&data, 1), Some(-2.0));
+        assert_eq!(ElementType::Float32.decode(&data, 2), None);
+
+        let data = (-3i32).to_le_bytes();
+        assert_eq!(ElementType::Sint32.decode(&data, 0), Some(-3.0));
+        assert_eq!(
+            ElementType::Uint32.decode(&data, 0),
+            Some(u32::MAX as f32 - 2.0)
+        );
+        assert_eq!(ElementType::Uint8.decode(&data, 1), Some(255.0));
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        // 10 floats in rows of 4: two full rows and a partial one
+        let layout = GridLayout::new(40, ElementType::Float32, 4, 1024).unwrap();
+        assert_eq!(layout.width, 4);
+        assert_eq!(layout.height, 3);
+        assert_eq!(layout.elements, 10);
+        assert_eq!(layout.truncated, 0);
+        assert_eq!(layout.index(1, 2), Some(9));
+        assert_eq!(layout.index(2, 2), None);
+        assert_eq!(layout.index(4, 0), None);
+
+        // Trailing bytes that do not make a whole element are ignored
+        let layout = GridLayout::new(7, ElementType::Uint32, 1, 1024).unwrap();
+        assert_eq!(layout.elements, 1);
+
+        assert!(GridLayout::new(3, ElementType::Float32, 4, 1024).is_none());
+        assert!(GridLayout::new(16, ElementType::Float32, 0, 1024).is_none());
+    }
+
+    #[test]
+    fn test_grid_layout_truncates_rows() {
+        let layout = GridLayout::new(100, ElementType::Uint8, 10, 4).unwrap();
+        assert_eq!(layout.height, 4);
+        assert_eq!(layout.elements, 40);
+        assert_eq!(layout.truncated, 60);
+    }
+
+    #[test]
+    fn test_value_range_skips_non_finite() {
+        let data: Vec<u8> = [3.0f32, f32::NAN, -1.0, f32::INFINITY, 2.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        assert_eq!(
+            value_range(&data, ElementType::Float32, 5),
+            Some((-1.0, 3.0))
+        );
+        assert_eq!(
+            value_range(&data, ElementType::Float32, 1),
+            Some((3.0, 3.0))
+        );
+        assert_eq!(value_range(&[], ElementType::Float32, 4), None);
+    }
+
+    #[test]
+    fn test_params_layout_matches_shader() {
+        // Eight 4-byte members, as declared in the WGSL Params struct
+        assert_eq!(std::mem::size_of::<ImageParams>(), 32);
+    }
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(SHADER).expect("shader parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("shader validates");
+    }
+}
@@ -1,5 +1,8 @@
 // Buffer inspector utilities for viewing GPU buffer contents
 
+use crate::buffer_image::{BufferImage, BufferImageSettings, Colormap, ElementType, GridLayout};
+use crate::device_recovery::ResourceRecreate;
+
 /// Format for displaying buffer data
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataFormat {
@@ -35,6 +38,15 @@ impl DataFormat {
     }
 }
 
+/// How the buffer contents are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewMode {
+    /// Formatted text in the selected [`DataFormat`]
+    Text,
+    /// False-color 2D grid, see [`crate::buffer_image`]
+    Image,
+}
+
 /// Inspector for viewing GPU buffer contents
 ///
 /// This utility allows reading and displaying the contents of GPU buffers
@@ -55,6 +67,18 @@ pub struct BufferInspector {
     is_loading: bool,
     /// Error message if loading failed
     error_message: Option<String>,
+    /// Whether the data is shown as text or as an image
+    view_mode: ViewMode,
+    /// Grid width, element type and colors of the image view
+    image_settings: BufferImageSettings,
+    /// Visualization pipeline, created the first time the image is shown
+    image: Option<BufferImage>,
+    /// Whether the image must be rendered again
+    image_dirty: bool,
+    /// Outcome of the last image render
+    image_result: Option<Result<GridLayout, String>>,
+    /// Screen pixels per image texel
+    image_zoom: f32,
 }
 
 impl Default for BufferInspector {
@@ -74,6 +98,12 @@ impl BufferInspector {
             max_display_bytes: 4096, // Show up to 4KB by default
             is_loading: false,
             error_message: None,
+            view_mode: ViewMode::Text,
+            image_settings: BufferImageSettings::default(),
+            image: None,
+            image_dirty: true,
+            image_result: None,
+            image_zoom: 4.0,
         }
     }
 
@@ -101,6 +131,7 @@ impl BufferInspector {
     pub fn clear(&mut self) {
         self.buffer_data.clear();
         self.error_message = None;
+        self.image_dirty = true;
     }
 
     /// Load buffer data synchronously (for testing or when buffer is already mapped)
@@ -111,6 +142,49 @@ impl BufferInspector {
         self.buffer_data = data;
         self.error_message = None;
         self.is_loading = false;
+        self.image_dirty = true;
+    }
+
+    /// Set whether the data is shown as text or as an image
+    pub fn set_view_mode(&mut self, mode: ViewMode) {
+        self.view_mode = mode;
+    }
+
+    /// Get the current view mode
+    pub fn view_mode(&self) -> ViewMode {
+        self.view_mode
+    }
+
+    /// Set how the data is laid out and colored in the image view
+    pub fn set_image_settings(&mut self, settings: BufferImageSettings) {
+        self.image_dirty |= settings != self.image_settings;
+        self.image_settings = settings;
+    }
+
+    /// Get the image view settings
+    pub fn image_settings(&self) -> &BufferImageSettings {
+        &self.image_settings
+    }
+
+    /// Render the image view if the data or its settings changed
+    ///
+    /// Returns the outcome of the latest render, or `None` while there is no
+    /// data to show.
+    pub fn update_image(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Option<&Result<GridLayout, String>> {
+        if self.image_dirty {
+            self.image_dirty = false;
+            self.image_result = if self.buffer_data.is_empty() {
+                None
+            } else {
+                let image = self.image.get_or_insert_with(|| BufferImage::new(device));
+                Some(image.render(device, queue, &self.buffer_data, &self.image_settings))
+            };
+        }
+        self.image_result.as_ref()
     }
 
     /// Set an error message
@@ -227,23 +301,76 @@ impl BufferInspector {
     }
 
     /// Render the buffer inspector UI
+    ///
+    /// The image view needs a device; use [`Self::ui_with_image`] to enable it.
     pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.ui_impl(ui, None);
+    }
+
+    /// Render the buffer inspector UI with the image view (Native version)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ui_with_image(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+        renderer: Option<&mut egui_wgpu::Renderer>,
+    ) {
+        let mut texture_id = None;
+        if let (ViewMode::Image, Some(device), Some(queue)) = (self.view_mode, device, queue) {
+            if matches!(self.update_image(device, queue), Some(Ok(_))) {
+                if let (Some(image), Some(renderer)) = (&mut self.image, renderer) {
+                    texture_id = image.get_texture_id(device, renderer);
+                }
+            }
+        }
+        self.ui_impl(ui, texture_id);
+    }
+
+    /// Render the buffer inspector UI with the image view (WASM version)
+    ///
+    /// The image is rendered, but egui cannot display native textures here,
+    /// so only the grid statistics are shown.
+    #[cfg(target_arch = "wasm32")]
+    pub fn ui_with_image(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+    ) {
+        if let (ViewMode::Image, Some(device), Some(queue)) = (self.view_mode, device, queue) {
+            self.update_image(device, queue);
+        }
+        self.ui_impl(ui, None);
+    }
+
+    fn ui_impl(&mut self, ui: &mut egui::Ui, image_texture: Option<egui::TextureId>) {
         ui.heading("Buffer Inspector");
         ui.separator();
 
-        // Format selection
         ui.horizontal(|ui| {
-            ui.label("Display format:");
-            for format in DataFormat::all() {
-                if ui
-                    .selectable_label(self.display_format == *format, format.as_str())
-                    .clicked()
-                {
-                    self.display_format = *format;
-                }
-            }
+            ui.label("View:");
+            ui.selectable_value(&mut self.view_mode, ViewMode::Text, "📝 Text");
+            ui.selectable_value(&mut self.view_mode, ViewMode::Image, "🖼 Image");
         });
 
+        // Format selection
+        if self.view_mode == ViewMode::Text {
+            ui.horizontal(|ui| {
+                ui.label("Display format:");
+                for format in DataFormat::all() {
+                    if ui
+                        .selectable_label(self.display_format == *format, format.as_str())
+                        .clicked()
+                    {
+                        self.display_format = *format;
+                    }
+                }
+            });
+        } else {
+            self.render_image_settings_ui(ui);
+        }
+
         ui.separator();
 
         // Display statistics
@@ -269,6 +396,8 @@ impl BufferInspector {
         if self.is_loading {
             ui.spinner();
             ui.label("Loading buffer data...");
+        } else if !self.buffer_data.is_empty() && self.view_mode == ViewMode::Image {
+            self.render_image_ui(ui, image_texture);
         } else if !self.buffer_data.is_empty() {
             egui::ScrollArea::vertical()
                 .max_height(400.0)
@@ -283,6 +412,154 @@ impl BufferInspector {
         } else if self.error_message.is_none() {
             ui.label("Select a buffer from the Resource Inspector to view its contents");
         }
+
+        if self.view_mode == ViewMode::Image && self.image_dirty {
+            // Settings changed this frame; show the new image on the next one
+            ui.ctx().request_repaint();
+        }
+    }
+
+    fn render_image_settings_ui(&mut self, ui: &mut egui::Ui) {
+        let mut settings = self.image_settings;
+        let elements = self.buffer_data.len() / settings.element_type.size();
+
+        ui.horizontal(|ui| {
+            ui.label("Element type:");
+            for element_type in ElementType::all() {
+                ui.selectable_value(
+                    &mut settings.element_type,
+                    *element_type,
+                    element_type.as_str(),
+                );
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Width:");
+            ui.add(
+                egui::DragValue::new(&mut settings.width)
+                    .range(1..=elements.max(1) as u32)
+                    .suffix(" elements"),
+            );
+            if elements > 0 {
+                let square = (elements as f64).sqrt().ceil() as u32;
+                if ui
+                    .small_button("◻ Square")
+                    .on_hover_text("Use a width that makes the grid roughly square")
+                    .clicked()
+                {
+                    settings.width = square;
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Colormap:");
+            for colormap in Colormap::all() {
+                ui.selectable_value(&mut settings.colormap, *colormap, colormap.as_str());
+            }
+        });
+        ui.horizontal(|ui| {
+            let mut auto_range = settings.range.is_none();
+            if ui
+                .checkbox(&mut auto_range, "Auto range")
+                .on_hover_text(
+                    "Map the smallest and largest finite value to the ends of the colormap",
+                )
+                .changed()
+            {
+                settings.range = if auto_range {
+                    None
+                } else {
+                    Some(self.image_range().unwrap_or((0.0, 1.0)))
+                };
+            }
+            if let Some((min, max)) = &mut settings.range {
+                ui.label("Min:");
+                ui.add(egui::DragValue::new(min).speed(0.01));
+                ui.label("Max:");
+                ui.add(egui::DragValue::new(max).speed(0.01));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Zoom:");
+            ui.add(egui::Slider::new(&mut self.image_zoom, 1.0..=32.0).logarithmic(true));
+        });
+
+        self.set_image_settings(settings);
+    }
+
+    /// Values the ends of the colormap stood for in the last image
+    fn image_range(&self) -> Option<(f32, f32)> {
+        self.image.as_ref().and_then(BufferImage::range)
+    }
+
+    fn render_image_ui(&mut self, ui: &mut egui::Ui, texture_id: Option<egui::TextureId>) {
+        let layout = match &self.image_result {
+            Some(Ok(layout)) => *layout,
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::RED, format!("Cannot show image: {}", e));
+                return;
+            }
+            None => {
+                ui.label("The image view needs a GPU device");
+                return;
+            }
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(format!("Grid: {} × {}", layout.width, layout.height));
+            if let Some((min, max)) = self.image_range() {
+                ui.separator();
+                ui.label(format!("Range: {} … {}", min, max));
+            }
+            if layout.truncated > 0 {
+                ui.separator();
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "{} elements past the size limits not shown",
+                        layout.truncated
+                    ),
+                );
+            }
+        });
+
+        let Some(texture_id) = texture_id else {
+            ui.label("Image preview is not available in this build");
+            return;
+        };
+        let size = egui::vec2(layout.width as f32, layout.height as f32) * self.image_zoom;
+        egui::ScrollArea::both().max_height(400.0).show(ui, |ui| {
+            let response = ui.add(
+                egui::Image::new(egui::load::SizedTexture::new(texture_id, size))
+                    .sense(egui::Sense::hover()),
+            );
+            if let Some(pos) = response.hover_pos() {
+                let cell = (pos - response.rect.min) / self.image_zoom;
+                let (x, y) = (cell.x as u32, cell.y as u32);
+                if let Some(index) = layout.index(x, y) {
+                    let value = self
+                        .image_settings
+                        .element_type
+                        .decode(&self.buffer_data, index);
+                    response.on_hover_text(format!(
+                        "({}, {}) element {}: {}",
+                        x,
+                        y,
+                        index,
+                        value.map_or("-".to_string(), |v| v.to_string())
+                    ));
+                }
+            }
+        });
+    }
+}
+
+impl ResourceRecreate for BufferInspector {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // The image pipeline and its egui texture belong to the old device
+        // and renderer; they are rebuilt the next time the image is shown
+        self.image = None;
+        self.image_dirty = true;
     }
 }
 
@@ -370,4 +647,32 @@ mod tests {
         let formatted = inspector.format_data();
         assert_eq!(formatted, "No data loaded");
     }
+
+    #[test]
+    fn test_image_settings_mark_dirty() {
+        let mut inspector = BufferInspector::new();
+        inspector.image_dirty = false;
+
+        inspector.set_image_settings(BufferImageSettings::default());
+        assert!(!inspector.image_dirty);
+
+        inspector.set_image_settings(BufferImageSettings {
+            width: 3,
+            ..BufferImageSettings::default()
+        });
+        assert!(inspector.image_dirty);
+        assert_eq!(inspector.image_settings().width, 3);
+
+        inspector.image_dirty = false;
+        inspector.load_data(vec![0; 16]);
+        assert!(inspector.image_dirty);
+    }
+
+    #[test]
+    fn test_view_mode() {
+        let mut inspector = BufferInspector::new();
+        assert_eq!(inspector.view_mode(), ViewMode::Text);
+        inspector.set_view_mode(ViewMode::Image);
+        assert_eq!(inspector.view_mode(), ViewMode::Image);
+    }
 }
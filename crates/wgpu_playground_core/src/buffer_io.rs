@@ -0,0 +1,395 @@
+//! Import and export of buffer contents as CSV or raw binary files
+//!
+//! CSV files are interpreted row by row: every column is mapped to a
+//! [`ColumnType`] and the values of a row are written back to back as
+//! little-endian 32-bit values, so a table with columns `x, y, z` becomes an
+//! array of `vec3<f32>`-like structs. Raw binary files are used as-is.
+
+use std::fmt;
+use std::path::Path;
+
+/// Errors that can occur while importing or exporting buffer data
+#[derive(Debug)]
+pub enum BufferIoError {
+    /// Reading or writing a file failed
+    Io(String),
+    /// A CSV cell could not be parsed as its column type
+    Parse {
+        /// 1-based line number in the CSV text
+        line: usize,
+        /// 1-based column number
+        column: usize,
+        message: String,
+    },
+    /// Column mapping does not fit the data
+    InvalidLayout(String),
+    /// Reading the buffer back from the GPU failed
+    Readback(String),
+}
+
+impl fmt::Display for BufferIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BufferIoError::Io(msg) => write!(f, "I/O error: {}", msg),
+            BufferIoError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "Line {}, column {}: {}", line, column, message),
+            BufferIoError::InvalidLayout(msg) => write!(f, "Invalid column layout: {}", msg),
+            BufferIoError::Readback(msg) => write!(f, "Buffer readback failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BufferIoError {}
+
+impl From<std::io::Error> for BufferIoError {
+    fn from(err: std::io::Error) -> Self {
+        BufferIoError::Io(err.to_string())
+    }
+}
+
+/// Scalar type a CSV column is stored as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// 32-bit float
+    F32,
+    /// 32-bit unsigned integer
+    U32,
+    /// 32-bit signed integer
+    I32,
+}
+
+impl ColumnType {
+    /// All column types
+    pub fn all() -> &'static [ColumnType] {
+        &[ColumnType::F32, ColumnType::U32, ColumnType::I32]
+    }
+
+    /// WGSL name of the type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnType::F32 => "f32",
+            ColumnType::U32 => "u32",
+            ColumnType::I32 => "i32",
+        }
+    }
+
+    /// Size of one value in bytes
+    pub fn size(&self) -> usize {
+        4
+    }
+
+    /// Parse a cell into little-endian bytes
+    fn encode(&self, cell: &str) -> Result<[u8; 4], String> {
+        match self {
+            ColumnType::F32 => cell
+                .parse::<f32>()
+                .map(f32::to_le_bytes)
+                .map_err(|_| format!("'{}' is not a valid f32", cell)),
+            ColumnType::U32 => cell
+                .parse::<u32>()
+                .map(u32::to_le_bytes)
+                .map_err(|_| format!("'{}' is not a valid u32", cell)),
+            ColumnType::I32 => cell
+                .parse::<i32>()
+                .map(i32::to_le_bytes)
+                .map_err(|_| format!("'{}' is not a valid i32", cell)),
+        }
+    }
+
+    /// Format little-endian bytes as a CSV cell
+    fn decode(&self, bytes: [u8; 4]) -> String {
+        match self {
+            ColumnType::F32 => f32::from_le_bytes(bytes).to_string(),
+            ColumnType::U32 => u32::from_le_bytes(bytes).to_string(),
+            ColumnType::I32 => i32::from_le_bytes(bytes).to_string(),
+        }
+    }
+}
+
+/// A parsed CSV file before its columns are mapped to types
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvTable {
+    /// Column names, if the first row was a header
+    pub header: Option<Vec<String>>,
+    /// Data rows as `(line number, cells)`
+    pub rows: Vec<(usize, Vec<String>)>,
+}
+
+impl CsvTable {
+    /// Parse comma-separated text
+    ///
+    /// Empty lines and lines starting with `#` are skipped. The first row is
+    /// treated as a header if any of its cells is not a number.
+    pub fn parse(text: &str) -> Result<Self, BufferIoError> {
+        let mut rows: Vec<(usize, Vec<String>)> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(n, line)| (n, line.split(',').map(|c| c.trim().to_string()).collect()))
+            .collect();
+
+        let header = match rows.first() {
+            Some((_, cells)) if cells.iter().any(|c| c.parse::<f64>().is_err()) => {
+                Some(rows.remove(0).1)
+            }
+            _ => None,
+        };
+
+        let table = Self { header, rows };
+        let columns = table.column_count();
+        if let Some((line, cells)) = table.rows.iter().find(|(_, c)| c.len() != columns) {
+            return Err(BufferIoError::Parse {
+                line: *line,
+                column: cells.len().min(columns) + 1,
+                message: format!("expected {} columns, found {}", columns, cells.len()),
+            });
+        }
+        Ok(table)
+    }
+
+    /// Number of columns
+    pub fn column_count(&self) -> usize {
+        self.header
+            .as_ref()
+            .map(Vec::len)
+            .or_else(|| self.rows.first().map(|(_, cells)| cells.len()))
+            .unwrap_or(0)
+    }
+
+    /// Name of a column, from the header or `column N`
+    pub fn column_name(&self, index: usize) -> String {
+        self.header
+            .as_ref()
+            .and_then(|h| h.get(index).cloned())
+            .unwrap_or_else(|| format!("column {}", index + 1))
+    }
+
+    /// Guess column types from the data
+    ///
+    /// Columns holding only non-negative integers become `u32`, integers
+    /// with a sign become `i32` and everything else `f32`.
+    pub fn infer_column_types(&self) -> Vec<ColumnType> {
+        (0..self.column_count())
+            .map(|column| {
+                let cells = || {
+                    self.rows
+                        .iter()
+                        .map(move |(_, cells)| cells[column].as_str())
+                };
+                if self.rows.is_empty() {
+                    ColumnType::F32
+                } else if cells().all(|c| c.parse::<u32>().is_ok()) {
+                    ColumnType::U32
+                } else if cells().all(|c| c.parse::<i32>().is_ok()) {
+                    ColumnType::I32
+                } else {
+                    ColumnType::F32
+                }
+            })
+            .collect()
+    }
+
+    /// Encode all rows with the given column types
+    pub fn to_bytes(&self, columns: &[ColumnType]) -> Result<Vec<u8>, BufferIoError> {
+        if columns.len() != self.column_count() {
+            return Err(BufferIoError::InvalidLayout(format!(
+                "{} column types for {} columns",
+                columns.len(),
+                self.column_count()
+            )));
+        }
+        let row_size: usize = columns.iter().map(ColumnType::size).sum();
+        let mut data = Vec::with_capacity(self.rows.len() * row_size);
+        for (line, cells) in &self.rows {
+            for (index, (cell, ty)) in cells.iter().zip(columns).enumerate() {
+                let bytes = ty.encode(cell).map_err(|message| BufferIoError::Parse {
+                    line: *line,
+                    column: index + 1,
+                    message,
+                })?;
+                data.extend_from_slice(&bytes);
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// Format buffer contents as CSV, one row per `columns` group
+///
+/// Trailing bytes that do not form a complete row are not exported.
+pub fn export_csv(
+    data: &[u8],
+    columns: &[ColumnType],
+    header: Option<&[String]>,
+) -> Result<String, BufferIoError> {
+    if columns.is_empty() {
+        return Err(BufferIoError::InvalidLayout(
+            "at least one column is required".to_string(),
+        ));
+    }
+    let row_size: usize = columns.iter().map(ColumnType::size).sum();
+
+    let mut csv = String::new();
+    if let Some(header) = header {
+        csv.push_str(&header.join(","));
+        csv.push('\n');
+    }
+    for row in data.chunks_exact(row_size) {
+        let cells: Vec<String> = row
+            .chunks_exact(4)
+            .zip(columns)
+            .map(|(bytes, ty)| ty.decode([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+/// Whether a path should be treated as CSV (by extension)
+pub fn is_csv_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"))
+}
+
+/// Pad data with zeros to `wgpu::COPY_BUFFER_ALIGNMENT`
+pub fn pad_to_copy_alignment(mut data: Vec<u8>) -> Vec<u8> {
+    let align = wgpu::COPY_BUFFER_ALIGNMENT as usize;
+    data.resize(data.len().div_ceil(align) * align, 0);
+    data
+}
+
+/// Read the full contents of a buffer back to the CPU
+///
+/// The buffer needs `COPY_SRC` usage; its contents are copied into a
+/// temporary staging buffer. Blocks until the GPU has finished.
+pub fn read_buffer(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    buffer: &wgpu::Buffer,
+) -> Result<Vec<u8>, BufferIoError> {
+    if !buffer.usage().contains(wgpu::BufferUsages::COPY_SRC) {
+        return Err(BufferIoError::Readback(
+            "buffer needs COPY_SRC usage to be exported".to_string(),
+        ));
+    }
+    let size = buffer.size();
+
+    let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Buffer Export Staging Buffer"),
+        size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Buffer Export Encoder"),
+    });
+    encoder.copy_buffer_to_buffer(buffer, 0, &staging_buffer, 0, size);
+    queue.submit(Some(encoder.finish()));
+
+    let buffer_slice = staging_buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    let _ = device.poll(wgpu::PollType::Wait {
+        submission_index: None,
+        timeout: None,
+    });
+
+    match receiver.recv() {
+        Ok(Ok(())) => {
+            let data = buffer_slice.get_mapped_range().to_vec();
+            staging_buffer.unmap();
+            Ok(data)
+        }
+        Ok(Err(e)) => Err(BufferIoError::Readback(e.to_string())),
+        Err(e) => Err(BufferIoError::Readback(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_with_header_and_infer_types() {
+        let table =
+            CsvTable::parse("# particles\nx, y, id, delta\n0.5, 1, 3, -2\n1.5, 2, 4, 7\n").unwrap();
+        assert_eq!(table.column_count(), 4);
+        assert_eq!(table.column_name(2), "id");
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(
+            table.infer_column_types(),
+            vec![
+                ColumnType::F32,
+                ColumnType::U32,
+                ColumnType::U32,
+                ColumnType::I32
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_bytes_interleaves_columns() {
+        let table = CsvTable::parse("1.0,2\n3.0,4\n").unwrap();
+        assert!(table.header.is_none());
+        let data = table.to_bytes(&[ColumnType::F32, ColumnType::U32]).unwrap();
+        assert_eq!(data.len(), 16);
+        assert_eq!(&data[0..4], &1.0f32.to_le_bytes());
+        assert_eq!(&data[4..8], &2u32.to_le_bytes());
+        assert_eq!(&data[8..12], &3.0f32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_parse_errors_report_location() {
+        let table = CsvTable::parse("a,b\n1,2\n3,x\n").unwrap();
+        match table.to_bytes(&[ColumnType::U32, ColumnType::U32]) {
+            Err(BufferIoError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), (3, 2));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert!(matches!(
+            CsvTable::parse("1,2\n3\n"),
+            Err(BufferIoError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            table.to_bytes(&[ColumnType::U32]),
+            Err(BufferIoError::InvalidLayout(_))
+        ));
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let csv = "x,count\n-1.25,7\n3.5,0\n";
+        let table = CsvTable::parse(csv).unwrap();
+        let columns = [ColumnType::F32, ColumnType::I32];
+        let data = table.to_bytes(&columns).unwrap();
+        let exported = export_csv(&data, &columns, table.header.as_deref()).unwrap();
+        assert_eq!(exported, csv);
+    }
+
+    #[test]
+    fn test_export_skips_partial_row() {
+        let mut data = 5u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&6u32.to_le_bytes());
+        data.extend_from_slice(&7u32.to_le_bytes());
+        let csv = export_csv(&data, &[ColumnType::U32, ColumnType::U32], None).unwrap();
+        assert_eq!(csv, "5,6\n");
+    }
+
+    #[test]
+    fn test_padding_and_csv_detection() {
+        assert_eq!(pad_to_copy_alignment(vec![1, 2, 3, 4, 5]).len(), 8);
+        assert_eq!(pad_to_copy_alignment(vec![1, 2, 3, 4]).len(), 4);
+        assert!(is_csv_path(Path::new("data/points.CSV")));
+        assert!(!is_csv_path(Path::new("data/points.bin")));
+    }
+}
@@ -0,0 +1,717 @@
+//! Cross-checks CPU struct layouts against shader buffer bindings
+//!
+//! A uniform or storage buffer filled from a `#[repr(C)]` Rust struct only
+//! works if every field lands where WGSL expects it. WGSL aligns `vec3` to
+//! 16 bytes and rounds struct sizes up to their alignment, so a struct of
+//! `[f32; 3]` fields that looks identical on the CPU silently reads
+//! garbage in the shader. This module computes the CPU layout from a Rust
+//! struct definition, reflects the shader's layout with naga and reports
+//! every field whose offset or size differs.
+//!
+//! CPU structs are written like Rust code:
+//!
+//! ```text
+//! #[repr(C)]
+//! struct Light {
+//!     position: [f32; 3],
+//!     color: [f32; 3],
+//!     intensity: f32,
+//! }
+//! ```
+//!
+//! Scalars (`f32`, `u32`, `i32`, `f16`), fixed-size arrays, common glam
+//! types and structs defined earlier in the same text are supported. The
+//! last struct is the one checked. Nested struct fields are compared as
+//! `outer.inner`.
+
+use std::fmt;
+
+use naga::proc::Layouter;
+use naga::{AddressSpace, ArraySize, Handle, Module, TypeInner};
+
+/// Error in a CPU struct definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpuLayoutError {
+    /// Line of the error (1-indexed)
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CpuLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for CpuLayoutError {}
+
+/// A field of a flattened struct layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldLayout {
+    /// Field name, `outer.inner` for fields of nested structs
+    pub name: String,
+    /// Byte offset from the start of the outermost struct
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// Byte layout of a struct, nested structs flattened into their fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLayout {
+    pub name: String,
+    pub fields: Vec<FieldLayout>,
+    /// Size including trailing padding, i.e. the array stride
+    pub size: u64,
+}
+
+/// A uniform or storage buffer binding of a shader
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderBufferBinding {
+    pub group: u32,
+    pub binding: u32,
+    /// Name of the global variable
+    pub name: String,
+    /// `uniform` or `storage`
+    pub space: &'static str,
+    /// Whether the binding is an array of the struct
+    pub is_array: bool,
+    /// Layout of the struct, or why there is no struct to compare
+    pub layout: Result<StructLayout, String>,
+}
+
+impl ShaderBufferBinding {
+    /// Short description like `@group(0) @binding(1) lights: storage`
+    pub fn label(&self) -> String {
+        format!(
+            "@group({}) @binding({}) {}: {}",
+            self.group, self.binding, self.name, self.space
+        )
+    }
+}
+
+/// A difference between the CPU and the shader layout
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutMismatch {
+    Offset {
+        field: String,
+        cpu: u64,
+        shader: u64,
+    },
+    Size {
+        field: String,
+        cpu: u64,
+        shader: u64,
+    },
+    MissingInShader {
+        field: String,
+    },
+    MissingOnCpu {
+        field: String,
+    },
+    /// Total struct size of a non-array binding
+    StructSize {
+        cpu: u64,
+        shader: u64,
+    },
+    /// Element stride of an array binding
+    Stride {
+        cpu: u64,
+        shader: u64,
+    },
+}
+
+impl fmt::Display for LayoutMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutMismatch::Offset { field, cpu, shader } => write!(
+                f,
+                "field `{}` offset {} on CPU vs {} in shader",
+                field, cpu, shader
+            ),
+            LayoutMismatch::Size { field, cpu, shader } => write!(
+                f,
+                "field `{}` size {} on CPU vs {} in shader",
+                field, cpu, shader
+            ),
+            LayoutMismatch::MissingInShader { field } => {
+                write!(f, "field `{}` exists on CPU but not in shader", field)
+            }
+            LayoutMismatch::MissingOnCpu { field } => {
+                write!(f, "field `{}` exists in shader but not on CPU", field)
+            }
+            LayoutMismatch::StructSize { cpu, shader } => {
+                write!(f, "struct size {} on CPU vs {} in shader", cpu, shader)
+            }
+            LayoutMismatch::Stride { cpu, shader } => {
+                write!(f, "array stride {} on CPU vs {} in shader", cpu, shader)
+            }
+        }
+    }
+}
+
+/// Compare a CPU layout with the layout of a shader binding
+///
+/// Fields are matched by name; CPU fields starting with `_` are padding
+/// and need no counterpart. An empty result means the CPU data can be
+/// uploaded as-is.
+pub fn check_layout(cpu: &StructLayout, shader: &ShaderBufferBinding) -> Vec<LayoutMismatch> {
+    let Ok(layout) = &shader.layout else {
+        return Vec::new();
+    };
+    let mut mismatches = Vec::new();
+    for field in &cpu.fields {
+        match layout.fields.iter().find(|f| f.name == field.name) {
+            Some(other) if other.offset != field.offset => {
+                mismatches.push(LayoutMismatch::Offset {
+                    field: field.name.clone(),
+                    cpu: field.offset,
+                    shader: other.offset,
+                })
+            }
+            Some(other) if other.size != field.size => mismatches.push(LayoutMismatch::Size {
+                field: field.name.clone(),
+                cpu: field.size,
+                shader: other.size,
+            }),
+            Some(_) => {}
+            // Leading underscores mark explicit padding
+            None if field.name.starts_with('_') => {}
+            None => mismatches.push(LayoutMismatch::MissingInShader {
+                field: field.name.clone(),
+            }),
+        }
+    }
+    for field in &layout.fields {
+        if !cpu.fields.iter().any(|f| f.name == field.name) {
+            mismatches.push(LayoutMismatch::MissingOnCpu {
+                field: field.name.clone(),
+            });
+        }
+    }
+    if cpu.size != layout.size {
+        mismatches.push(if shader.is_array {
+            LayoutMismatch::Stride {
+                cpu: cpu.size,
+                shader: layout.size,
+            }
+        } else {
+            LayoutMismatch::StructSize {
+                cpu: cpu.size,
+                shader: layout.size,
+            }
+        });
+    }
+    mismatches
+}
+
+/// Check a CPU struct against the binding at `group`/`binding` of a WGSL shader
+pub fn check_binding(
+    wgsl: &str,
+    group: u32,
+    binding: u32,
+    cpu_struct: &str,
+) -> Result<Vec<LayoutMismatch>, String> {
+    let cpu = parse_cpu_struct(cpu_struct).map_err(|e| e.to_string())?;
+    let module = naga::front::wgsl::parse_str(wgsl).map_err(|e| e.emit_to_string(wgsl))?;
+    let bindings = reflect_buffer_bindings(&module)?;
+    let shader = bindings
+        .iter()
+        .find(|b| b.group == group && b.binding == binding)
+        .ok_or_else(|| {
+            format!(
+                "Shader has no buffer at @group({}) @binding({})",
+                group, binding
+            )
+        })?;
+    shader.layout.as_ref().map_err(Clone::clone)?;
+    Ok(check_layout(&cpu, shader))
+}
+
+/// Uniform and storage buffer bindings of a module, by group and binding
+pub fn reflect_buffer_bindings(module: &Module) -> Result<Vec<ShaderBufferBinding>, String> {
+    let mut layouter = Layouter::default();
+    layouter
+        .update(module.to_ctx())
+        .map_err(|e| format!("Failed to compute type layouts: {}", e))?;
+
+    let mut bindings: Vec<ShaderBufferBinding> = module
+        .global_variables
+        .iter()
+        .filter_map(|(_, var)| {
+            let space = match var.space {
+                AddressSpace::Uniform => "uniform",
+                AddressSpace::Storage { .. } => "storage",
+                _ => return None,
+            };
+            let resource = var.binding.as_ref()?;
+            let (element, is_array) = match &module.types[var.ty].inner {
+                TypeInner::Array { base, .. } => (*base, true),
+                _ => (var.ty, false),
+            };
+            let layout = match &module.types[element].inner {
+                TypeInner::Struct { .. } => {
+                    let mut fields = Vec::new();
+                    flatten_shader_struct(module, &layouter, element, 0, "", &mut fields);
+                    Ok(StructLayout {
+                        name: type_name(module, element),
+                        fields,
+                        size: match &module.types[var.ty].inner {
+                            TypeInner::Array { stride, .. } => (*stride).into(),
+                            _ => layouter[element].size.into(),
+                        },
+                    })
+                }
+                _ => Err(format!(
+                    "`{}` is not a struct or an array of structs",
+                    type_name(module, var.ty)
+                )),
+            };
+            Some(ShaderBufferBinding {
+                group: resource.group,
+                binding: resource.binding,
+                name: var.name.clone().unwrap_or_default(),
+                space,
+                is_array,
+                layout,
+            })
+        })
+        .collect();
+    bindings.sort_by_key(|b| (b.group, b.binding));
+    Ok(bindings)
+}
+
+fn flatten_shader_struct(
+    module: &Module,
+    layouter: &Layouter,
+    ty: Handle<naga::Type>,
+    base_offset: u64,
+    prefix: &str,
+    fields: &mut Vec<FieldLayout>,
+) {
+    let TypeInner::Struct { members, .. } = &module.types[ty].inner else {
+        return;
+    };
+    for member in members {
+        let name = format!("{}{}", prefix, member.name.as_deref().unwrap_or("_"));
+        let offset = base_offset + u64::from(member.offset);
+        if matches!(module.types[member.ty].inner, TypeInner::Struct { .. }) {
+            let prefix = format!("{}.", name);
+            flatten_shader_struct(module, layouter, member.ty, offset, &prefix, fields);
+        } else {
+            fields.push(FieldLayout {
+                name,
+                offset,
+                size: layouter[member.ty].size.into(),
+            });
+        }
+    }
+}
+
+fn type_name(module: &Module, ty: Handle<naga::Type>) -> String {
+    let ty = &module.types[ty];
+    if let Some(name) = &ty.name {
+        return name.clone();
+    }
+    match &ty.inner {
+        TypeInner::Array {
+            size: ArraySize::Dynamic,
+            ..
+        } => "runtime-sized array".to_string(),
+        TypeInner::Array { .. } => "array".to_string(),
+        TypeInner::Scalar(_) => "scalar".to_string(),
+        TypeInner::Vector { .. } => "vector".to_string(),
+        TypeInner::Matrix { .. } => "matrix".to_string(),
+        _ => "type".to_string(),
+    }
+}
+
+/// Size and alignment of a CPU type
+#[derive(Debug, Clone)]
+struct CpuType {
+    size: u64,
+    align: u64,
+    /// Fields relative to the start of the type, for structs
+    fields: Vec<FieldLayout>,
+}
+
+impl CpuType {
+    fn plain(size: u64, align: u64) -> Self {
+        Self {
+            size,
+            align,
+            fields: Vec::new(),
+        }
+    }
+}
+
+/// Size and alignment of a built-in CPU type
+///
+/// glam's SIMD types (`Vec3A`, `Vec4`, `Quat`, `Mat3A`, `Mat4`) are
+/// 16-byte aligned; the others only need 4.
+fn builtin_type(name: &str) -> Option<CpuType> {
+    let (size, align) = match name {
+        "f16" => (2, 2),
+        "f32" | "u32" | "i32" => (4, 4),
+        "Vec2" | "UVec2" | "IVec2" => (8, 4),
+        "Vec3" | "UVec3" | "IVec3" => (12, 4),
+        "UVec4" | "IVec4" => (16, 4),
+        "Vec3A" | "Vec4" | "Quat" => (16, 16),
+        "Mat2" => (16, 16),
+        "Mat3" => (36, 4),
+        "Mat3A" => (48, 16),
+        "Mat4" => (64, 16),
+        _ => return None,
+    };
+    Some(CpuType::plain(size, align))
+}
+
+fn align_to(value: u64, align: u64) -> u64 {
+    value.div_ceil(align) * align
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Punct(char),
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, CpuLayoutError> {
+    let mut tokens = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let code = line.split("//").next().unwrap_or("");
+        // Attributes such as #[repr(C)] and #[derive(...)] do not matter
+        if code.trim_start().starts_with("#[") {
+            continue;
+        }
+        let mut chars = code.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+            if c.is_ascii_alphabetic() || c == '_' {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push((Token::Ident(code[start..end].to_string()), line_number));
+            } else if c.is_ascii_digit() {
+                let mut end = start + 1;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '_') {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                let digits = code[start..end].replace('_', "");
+                let number = digits.parse().map_err(|_| CpuLayoutError {
+                    line: line_number,
+                    message: format!("invalid number '{}'", digits),
+                })?;
+                tokens.push((Token::Number(number), line_number));
+            } else {
+                tokens.push((Token::Punct(c), line_number));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    structs: Vec<(String, CpuType)>,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error(&self, message: impl Into<String>) -> CpuLayoutError {
+        CpuLayoutError {
+            line: self.line(),
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), CpuLayoutError> {
+        if self.eat(&Token::Punct(c)) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", c)))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, CpuLayoutError> {
+        match self.peek() {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.position += 1;
+                Ok(name)
+            }
+            _ => Err(self.error("expected a name")),
+        }
+    }
+
+    fn skip_visibility(&mut self) {
+        if self.eat(&Token::Ident("pub".to_string())) && self.peek() == Some(&Token::Punct('(')) {
+            // pub(crate) and friends
+            while !matches!(self.next(), Some(Token::Punct(')')) | None) {}
+        }
+    }
+
+    fn parse_struct(&mut self) -> Result<(), CpuLayoutError> {
+        self.skip_visibility();
+        if self.ident()? != "struct" {
+            self.position -= 1;
+            return Err(self.error("expected 'struct'"));
+        }
+        let name = self.ident()?;
+        self.expect('{')?;
+
+        let mut offset = 0;
+        let mut align = 1;
+        let mut fields = Vec::new();
+        while !self.eat(&Token::Punct('}')) {
+            self.skip_visibility();
+            let field = self.ident()?;
+            self.expect(':')?;
+            let ty = self.parse_type()?;
+            offset = align_to(offset, ty.align);
+            align = align.max(ty.align);
+            if ty.fields.is_empty() {
+                fields.push(FieldLayout {
+                    name: field,
+                    offset,
+                    size: ty.size,
+                });
+            } else {
+                fields.extend(ty.fields.iter().map(|inner| FieldLayout {
+                    name: format!("{}.{}", field, inner.name),
+                    offset: offset + inner.offset,
+                    size: inner.size,
+                }));
+            }
+            offset += ty.size;
+            if !self.eat(&Token::Punct(',')) && self.peek() != Some(&Token::Punct('}')) {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+        let ty = CpuType {
+            size: align_to(offset, align),
+            align,
+            fields,
+        };
+        self.structs.push((name, ty));
+        Ok(())
+    }
+
+    fn parse_type(&mut self) -> Result<CpuType, CpuLayoutError> {
+        if self.eat(&Token::Punct('[')) {
+            let element = self.parse_type()?;
+            self.expect(';')?;
+            let count = match self.next() {
+                Some(Token::Number(count)) => count,
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("expected an array length"));
+                }
+            };
+            self.expect(']')?;
+            return Ok(CpuType::plain(element.size * count, element.align));
+        }
+
+        let mut name = self.ident()?;
+        // Accept paths like glam::Vec3
+        while self.peek() == Some(&Token::Punct(':')) {
+            self.expect(':')?;
+            self.expect(':')?;
+            name = self.ident()?;
+        }
+        if let Some(ty) = builtin_type(&name) {
+            return Ok(ty);
+        }
+        if let Some((_, ty)) = self.structs.iter().find(|(known, _)| *known == name) {
+            return Ok(ty.clone());
+        }
+        self.position -= 1;
+        Err(self.error(format!("unknown type '{}'", name)))
+    }
+}
+
+/// Compute the `#[repr(C)]` layout of the last struct in `source`
+pub fn parse_cpu_struct(source: &str) -> Result<StructLayout, CpuLayoutError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+        structs: Vec::new(),
+    };
+    while parser.peek().is_some() {
+        parser.parse_struct()?;
+    }
+    let (name, ty) = parser.structs.pop().ok_or(CpuLayoutError {
+        line: 1,
+        message: "no struct defined".to_string(),
+    })?;
+    Ok(StructLayout {
+        name,
+        fields: ty.fields,
+        size: ty.size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CPU_LIGHT: &str = "#[repr(C)]
+struct Light {
+    position: [f32; 3],
+    color: [f32; 3],
+    intensity: f32,
+}";
+
+    const SHADER: &str = "
+struct Light {
+    position: vec3<f32>,
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+struct Params {
+    count: u32,
+    scale: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> lights: array<Light>;
+@group(1) @binding(0) var<storage, read_write> values: array<f32>;
+
+@compute @workgroup_size(1)
+fn main() {
+    let light = lights[0];
+    values[0] = light.intensity * params.scale + f32(params.count);
+}
+";
+
+    fn bindings() -> Vec<ShaderBufferBinding> {
+        let module = naga::front::wgsl::parse_str(SHADER).unwrap();
+        reflect_buffer_bindings(&module).unwrap()
+    }
+
+    #[test]
+    fn test_cpu_layout() {
+        let layout = parse_cpu_struct(CPU_LIGHT).unwrap();
+        assert_eq!(layout.name, "Light");
+        let offsets: Vec<u64> = layout.fields.iter().map(|f| f.offset).collect();
+        assert_eq!(offsets, [0, 12, 24]);
+        assert_eq!(layout.size, 28);
+    }
+
+    #[test]
+    fn test_cpu_layout_padding_and_nesting() {
+        let layout = parse_cpu_struct(
+            "struct Inner { a: f32, b: glam::Vec4 }\n\
+             pub struct Outer {\n    pub flag: u32,\n    inner: Inner, // nested\n    tail: [u32; 2],\n}",
+        )
+        .unwrap();
+        let fields: Vec<(&str, u64, u64)> = layout
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset, f.size))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("flag", 0, 4),
+                ("inner.a", 16, 4),
+                ("inner.b", 32, 16),
+                ("tail", 48, 8),
+            ]
+        );
+        assert_eq!(layout.size, 64);
+    }
+
+    #[test]
+    fn test_cpu_layout_errors() {
+        let error = parse_cpu_struct("struct A {\n    x: f64,\n}").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(error.message.contains("f64"));
+        assert!(parse_cpu_struct("").is_err());
+        assert!(parse_cpu_struct("struct A { x: f32 y: f32 }").is_err());
+    }
+
+    #[test]
+    fn test_reflect_bindings() {
+        let bindings = bindings();
+        assert_eq!(bindings.len(), 3);
+        assert_eq!(bindings[0].label(), "@group(0) @binding(0) params: uniform");
+
+        let lights = &bindings[1];
+        assert!(lights.is_array);
+        let layout = lights.layout.as_ref().unwrap();
+        assert_eq!(layout.name, "Light");
+        let offsets: Vec<u64> = layout.fields.iter().map(|f| f.offset).collect();
+        assert_eq!(offsets, [0, 16, 28]);
+        assert_eq!(layout.size, 32);
+
+        assert!(bindings[2].layout.is_err());
+    }
+
+    #[test]
+    fn test_mismatches_reported() {
+        let cpu = parse_cpu_struct(CPU_LIGHT).unwrap();
+        let mismatches = check_layout(&cpu, &bindings()[1]);
+        let messages: Vec<String> = mismatches.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "field `color` offset 12 on CPU vs 16 in shader",
+                "field `intensity` offset 24 on CPU vs 28 in shader",
+                "array stride 28 on CPU vs 32 in shader",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matching_layout() {
+        let cpu = parse_cpu_struct(
+            "struct Light { position: [f32; 3], _pad: f32, color: [f32; 3], intensity: f32 }",
+        )
+        .unwrap();
+        assert!(check_layout(&cpu, &bindings()[1]).is_empty());
+
+        let result = check_binding(SHADER, 0, 0, "struct Params { count: u32, scale: f32 }");
+        assert_eq!(result, Ok(Vec::new()));
+        assert!(check_binding(SHADER, 3, 0, "struct P { a: f32 }").is_err());
+    }
+}
@@ -1,5 +1,12 @@
 use crate::buffer::{BufferDescriptor, BufferUsages};
+use crate::buffer_io::{self, BufferIoError, ColumnType, CsvTable};
 use crate::buffer_preview::BufferPreviewState;
+use crate::capability_snapshot::ProjectDescriptor;
+use crate::device_recovery::ResourceRecreate;
+use crate::error::Error;
+use crate::i18n::{tr, tr_args};
+use crate::memory_budget::MemoryBudget;
+use crate::operation_errors::OperationErrors;
 use crate::tooltip::{buffer_usage, property, TooltipExt};
 
 /// UI panel for creating and configuring GPU buffers
@@ -31,8 +38,28 @@ pub struct BufferPanel {
     preview_state: Option<BufferPreviewState>,
     /// Whether preview is enabled
     show_preview: bool,
+    /// Path for data import/export
+    data_path: String,
+    /// Imported initial contents of the buffer
+    initial_data: Option<Vec<u8>>,
+    /// CSV waiting for its column types to be confirmed
+    pending_csv: Option<CsvTable>,
+    /// Column types used for CSV import and export
+    column_types: Vec<ColumnType>,
+    /// Import/export result message
+    io_message: Option<String>,
+    /// Most recently created buffer, used for export
+    created_buffer: Option<wgpu::Buffer>,
+    /// Device errors from creating the buffer
+    gpu_errors: OperationErrors,
+    /// Budget new buffers are checked against in the simulated
+    /// out-of-memory mode
+    memory_budget: MemoryBudget,
 }
 
+/// Operation name of buffer creation in [`OperationErrors`]
+const CREATE_BUFFER: &str = "create_buffer";
+
 impl Default for BufferPanel {
     fn default() -> Self {
         Self::new()
@@ -61,6 +88,14 @@ impl BufferPanel {
             success_message: None,
             preview_state: None,
             show_preview: true,
+            data_path: "buffer_data.csv".to_string(),
+            initial_data: None,
+            pending_csv: None,
+            column_types: vec![ColumnType::F32],
+            io_message: None,
+            created_buffer: None,
+            gpu_errors: OperationErrors::new(),
+            memory_budget: MemoryBudget::new(),
         }
     }
 
@@ -68,8 +103,21 @@ impl BufferPanel {
     fn update_descriptor(&mut self) {
         // Parse size
         let size = self.size_input.parse::<u64>().unwrap_or(0);
+        let usage = self.build_usage_flags();
+
+        // Create descriptor
+        let label = if self.label_input.is_empty() {
+            None
+        } else {
+            Some(self.label_input.as_str())
+        };
+
+        self.descriptor = BufferDescriptor::new(label, size, usage)
+            .with_mapped_at_creation(self.mapped_at_creation);
+    }
 
-        // Build usage flags
+    /// Build usage flags from current UI state
+    fn build_usage_flags(&self) -> BufferUsages {
         let mut usage = BufferUsages::empty();
         if self.usage_vertex {
             usage |= BufferUsages::VERTEX;
@@ -101,16 +149,19 @@ impl BufferPanel {
         if self.usage_query_resolve {
             usage |= BufferUsages::QUERY_RESOLVE;
         }
+        usage
+    }
 
-        // Create descriptor
-        let label = if self.label_input.is_empty() {
-            None
-        } else {
-            Some(self.label_input.as_str())
-        };
-
-        self.descriptor = BufferDescriptor::new(label, size, usage)
-            .with_mapped_at_creation(self.mapped_at_creation);
+    /// The configured buffer, for checks against a virtual device
+    ///
+    /// `None` while the size is not a number.
+    pub fn project_descriptor(&self) -> Option<ProjectDescriptor> {
+        let size = self.size_input.parse::<u64>().ok()?;
+        Some(ProjectDescriptor::Buffer {
+            label: self.label_input.clone(),
+            size,
+            usage: self.build_usage_flags().to_wgpu(),
+        })
     }
 
     /// Validate the current configuration
@@ -137,23 +188,334 @@ impl BufferPanel {
             return None;
         }
 
-        match self.descriptor.create_buffer(device) {
+        // A simulated budget refuses the buffer the way the device would
+        if let Err(e) = self.memory_budget.check_allocation(self.descriptor.size()) {
+            self.gpu_errors
+                .insert(CREATE_BUFFER, Error::out_of_memory(e.to_string()));
+            self.success_message = None;
+            self.created_buffer = None;
+            return None;
+        }
+
+        let result = self
+            .gpu_errors
+            .scoped(device, CREATE_BUFFER, || match &self.initial_data {
+                Some(data) => self.create_buffer_with_data(device, data),
+                None => self.descriptor.create_buffer(device),
+            });
+
+        match result {
+            // The device rejected the descriptor; the error is shown inline
+            Ok(_) if self.gpu_errors.has_error(CREATE_BUFFER) => {
+                self.success_message = None;
+                self.created_buffer = None;
+                None
+            }
             Ok(buffer) => {
-                self.success_message = Some(format!(
-                    "✓ Buffer created successfully: {} bytes",
-                    self.descriptor.size()
+                self.success_message = Some(tr_args(
+                    "buffer-created",
+                    &[("size", &self.descriptor.size())],
                 ));
                 self.validation_error = None;
+                self.created_buffer = Some(buffer.clone());
                 Some(buffer)
             }
             Err(e) => {
-                self.validation_error = Some(format!("Failed to create buffer: {}", e));
+                self.validation_error = Some(tr_args("buffer-create-failed", &[("error", &e)]));
                 self.success_message = None;
                 None
             }
         }
     }
 
+    /// Check new buffers against the current state of `budget`
+    pub fn set_memory_budget(&mut self, budget: &MemoryBudget) {
+        self.memory_budget = budget.clone();
+    }
+
+    /// Create the buffer initialized with imported data, zero-padded to the configured size
+    fn create_buffer_with_data(
+        &self,
+        device: &wgpu::Device,
+        data: &[u8],
+    ) -> Result<wgpu::Buffer, crate::buffer::BufferError> {
+        use wgpu::util::DeviceExt;
+
+        let size = self.descriptor.size() as usize;
+        if size < data.len() {
+            return Err(crate::buffer::BufferError::InvalidSize(format!(
+                "size {} is smaller than the {} bytes of imported data",
+                size,
+                data.len()
+            )));
+        }
+        let mut contents = data.to_vec();
+        contents.resize(size, 0);
+        Ok(
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: self.descriptor.label(),
+                contents: &contents,
+                usage: self.descriptor.usage().to_wgpu(),
+            }),
+        )
+    }
+
+    /// Imported initial contents, if any
+    pub fn initial_data(&self) -> Option<&[u8]> {
+        self.initial_data.as_deref()
+    }
+
+    /// Use `data` as the initial buffer contents and size the buffer to fit
+    pub fn set_initial_data(&mut self, data: Vec<u8>) {
+        let data = buffer_io::pad_to_copy_alignment(data);
+        self.size_input = data.len().to_string();
+        self.initial_data = Some(data);
+    }
+
+    /// Import initial contents from a CSV or raw binary file
+    ///
+    /// Binary files are used directly. CSV files are parsed and their column
+    /// types inferred; call [`Self::confirm_csv_import`] to apply them.
+    pub fn import_file(&mut self, path: &std::path::Path) -> Result<(), BufferIoError> {
+        if buffer_io::is_csv_path(path) {
+            let table = CsvTable::parse(&std::fs::read_to_string(path)?)?;
+            self.column_types = table.infer_column_types();
+            self.pending_csv = Some(table);
+        } else {
+            self.set_initial_data(std::fs::read(path)?);
+        }
+        Ok(())
+    }
+
+    /// Encode the pending CSV with the chosen column types
+    pub fn confirm_csv_import(&mut self) -> Result<usize, BufferIoError> {
+        let Some(table) = &self.pending_csv else {
+            return Err(BufferIoError::InvalidLayout(
+                "no CSV import in progress".to_string(),
+            ));
+        };
+        let rows = table.rows.len();
+        let data = table.to_bytes(&self.column_types)?;
+        self.pending_csv = None;
+        self.set_initial_data(data);
+        Ok(rows)
+    }
+
+    /// Export buffer contents to a CSV or raw binary file
+    ///
+    /// Reads the last created buffer back from the GPU when a device is
+    /// available (e.g. after a compute dispatch wrote to it), otherwise
+    /// exports the imported data. Returns the number of bytes exported.
+    pub fn export_file(
+        &self,
+        path: &std::path::Path,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+    ) -> Result<usize, BufferIoError> {
+        let data = match (&self.created_buffer, device, queue) {
+            (Some(buffer), Some(device), Some(queue)) => {
+                buffer_io::read_buffer(device, queue, buffer)?
+            }
+            _ => self.initial_data.clone().ok_or_else(|| {
+                BufferIoError::InvalidLayout(
+                    "nothing to export, create a buffer or import data first".to_string(),
+                )
+            })?,
+        };
+
+        if buffer_io::is_csv_path(path) {
+            std::fs::write(
+                path,
+                buffer_io::export_csv(&data, &self.column_types, None)?,
+            )?;
+        } else {
+            std::fs::write(path, &data)?;
+        }
+        Ok(data.len())
+    }
+
+    /// Render the import/export section
+    fn render_data_io(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+    ) {
+        ui.group(|ui| {
+            ui.heading(tr("buffer-data-heading"));
+            ui.label(tr("buffer-data-intro"));
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label(tr("buffer-data-file"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.data_path)
+                        .desired_width(220.0)
+                        .hint_text(tr("buffer-data-file-hint")),
+                )
+                .on_hover_text(tr("buffer-data-file-hover"));
+
+                if ui.button(tr("buffer-data-import")).clicked() {
+                    let path = std::path::PathBuf::from(self.data_path.trim());
+                    self.io_message = Some(match self.import_file(&path) {
+                        Ok(()) if self.pending_csv.is_some() => tr("buffer-data-choose-columns"),
+                        Ok(()) => tr_args(
+                            "buffer-data-imported",
+                            &[
+                                ("bytes", &self.initial_data.as_ref().map_or(0, Vec::len)),
+                                ("path", &path.display()),
+                            ],
+                        ),
+                        Err(e) => tr_args("buffer-data-failed", &[("error", &e)]),
+                    });
+                }
+
+                if ui
+                    .button(tr("buffer-data-export"))
+                    .on_hover_text(tr("buffer-data-export-hover"))
+                    .clicked()
+                {
+                    let path = std::path::PathBuf::from(self.data_path.trim());
+                    self.io_message = Some(match self.export_file(&path, device, queue) {
+                        Ok(bytes) => tr_args(
+                            "buffer-data-exported",
+                            &[("bytes", &bytes), ("path", &path.display())],
+                        ),
+                        Err(e) => tr_args("buffer-data-failed", &[("error", &e)]),
+                    });
+                }
+
+                if self.initial_data.is_some() && ui.button(tr("buffer-data-clear")).clicked() {
+                    self.initial_data = None;
+                    self.io_message = None;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(tr("buffer-data-columns"));
+                let mut remove = None;
+                for (index, ty) in self.column_types.iter_mut().enumerate() {
+                    Self::column_type_combo(ui, ("buffer_csv_column", index), ty);
+                    if self.pending_csv.is_none()
+                        && ui
+                            .small_button("🗑")
+                            .on_hover_text(tr("buffer-data-remove-column"))
+                            .clicked()
+                    {
+                        remove = Some(index);
+                    }
+                }
+                if let Some(index) = remove {
+                    if self.column_types.len() > 1 {
+                        self.column_types.remove(index);
+                    }
+                }
+                if self.pending_csv.is_none() && ui.small_button("➕").clicked() {
+                    self.column_types.push(ColumnType::F32);
+                }
+            });
+
+            if let Some(data) = &self.initial_data {
+                ui.monospace(tr_args("buffer-data-initial", &[("bytes", &data.len())]));
+            }
+
+            if let Some(msg) = &self.io_message {
+                let color = if msg.starts_with('✓') {
+                    egui::Color32::GREEN
+                } else if msg.starts_with('✗') {
+                    egui::Color32::RED
+                } else {
+                    egui::Color32::from_rgb(200, 200, 100)
+                };
+                ui.colored_label(color, msg);
+            }
+        });
+
+        self.render_column_dialog(ui.ctx());
+    }
+
+    /// Dialog for mapping the columns of a pending CSV import to types
+    fn render_column_dialog(&mut self, ctx: &egui::Context) {
+        let Some(table) = &self.pending_csv else {
+            return;
+        };
+
+        let mut open = true;
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr("buffer-csv-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr_args("buffer-csv-rows", &[("rows", &table.rows.len())]));
+                ui.add_space(5.0);
+
+                egui::Grid::new("buffer_csv_column_mapping")
+                    .num_columns(3)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(tr("buffer-csv-column")).strong());
+                        ui.label(egui::RichText::new(tr("buffer-csv-type")).strong());
+                        ui.label(egui::RichText::new(tr("buffer-csv-first-value")).strong());
+                        ui.end_row();
+
+                        for (index, ty) in self.column_types.iter_mut().enumerate() {
+                            ui.label(table.column_name(index));
+                            Self::column_type_combo(ui, ("buffer_csv_mapping", index), ty);
+                            ui.monospace(
+                                table
+                                    .rows
+                                    .first()
+                                    .map(|(_, cells)| cells[index].as_str())
+                                    .unwrap_or("-"),
+                            );
+                            ui.end_row();
+                        }
+                    });
+
+                let stride: usize = self.column_types.iter().map(ColumnType::size).sum();
+                ui.add_space(5.0);
+                ui.label(tr_args(
+                    "buffer-csv-stride",
+                    &[("stride", &stride), ("total", &(stride * table.rows.len()))],
+                ));
+
+                ui.horizontal(|ui| {
+                    confirmed = ui.button(tr("buffer-csv-import")).clicked();
+                    cancelled = ui.button(tr("buffer-csv-cancel")).clicked();
+                });
+            });
+
+        if confirmed {
+            self.io_message = Some(match self.confirm_csv_import() {
+                Ok(rows) => tr_args(
+                    "buffer-csv-imported",
+                    &[
+                        ("rows", &rows),
+                        ("bytes", &self.initial_data.as_ref().map_or(0, Vec::len)),
+                    ],
+                ),
+                Err(e) => tr_args("buffer-data-failed", &[("error", &e)]),
+            });
+        } else if cancelled || !open {
+            self.pending_csv = None;
+            self.io_message = None;
+        }
+    }
+
+    fn column_type_combo(ui: &mut egui::Ui, id: impl std::hash::Hash, ty: &mut ColumnType) {
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(ty.as_str())
+            .width(60.0)
+            .show_ui(ui, |ui| {
+                for option in ColumnType::all() {
+                    ui.selectable_value(ty, *option, option.as_str());
+                }
+            });
+    }
+
     /// Render the buffer configuration UI
     ///
     /// This is a convenience wrapper that delegates to ui_with_preview() with None values.
@@ -176,25 +538,27 @@ impl BufferPanel {
         renderer: Option<&mut egui_wgpu::Renderer>,
     ) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("📐 Buffer Configuration");
-            ui.label("Configure and create GPU buffers with custom parameters.");
+            ui.heading(tr("buffer-heading"));
+            ui.label(tr("buffer-intro"));
             ui.add_space(10.0);
 
             // Buffer Label
             ui.group(|ui| {
-                ui.heading("Buffer Properties");
+                ui.heading(tr("buffer-properties"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("buffer_properties")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Label:")
-                            .webgpu_tooltip("Optional label for debugging and identification", Some("#dom-gpuobjectbase-label"));
+                        ui.label(tr("buffer-label")).webgpu_tooltip(
+                            &tr("buffer-label-tooltip"),
+                            Some("#dom-gpuobjectbase-label"),
+                        );
                         ui.text_edit_singleline(&mut self.label_input);
                         ui.end_row();
 
-                        property::BUFFER_SIZE.apply(ui.label("Size (bytes):"));
+                        property::BUFFER_SIZE.apply(ui.label(tr("buffer-size")));
                         ui.text_edit_singleline(&mut self.size_input);
                         ui.end_row();
                     });
@@ -204,8 +568,8 @@ impl BufferPanel {
 
             // Usage Flags
             ui.group(|ui| {
-                ui.heading("Usage Flags");
-                ui.label("Select how the buffer will be used (multiple flags can be selected):");
+                ui.heading(tr("buffer-usage-heading"));
+                ui.label(tr("buffer-usage-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("usage_flags")
@@ -228,7 +592,7 @@ impl BufferPanel {
                 ui.add_space(5.0);
                 ui.colored_label(
                     egui::Color32::from_rgb(200, 200, 100),
-                    "💡 Note: MAP_READ and MAP_WRITE cannot be used together"
+                    tr("buffer-usage-map-note"),
                 );
             });
 
@@ -236,43 +600,45 @@ impl BufferPanel {
 
             // Additional Options
             ui.group(|ui| {
-                ui.heading("Additional Options");
+                ui.heading(tr("buffer-options-heading"));
                 ui.add_space(5.0);
 
-                property::BUFFER_MAPPED_AT_CREATION.apply(
-                    ui.checkbox(&mut self.mapped_at_creation, "Mapped at creation")
-                );
+                property::BUFFER_MAPPED_AT_CREATION.apply(ui.checkbox(
+                    &mut self.mapped_at_creation,
+                    tr("buffer-mapped-at-creation"),
+                ));
             });
 
             ui.add_space(15.0);
 
             // Validation and Creation
             ui.horizontal(|ui| {
-                if ui.button("🔍 Validate").clicked() {
+                if ui.button(tr("buffer-validate")).clicked() {
                     self.validate();
                 }
 
-                if ui.button("✨ Create Buffer").clicked() {
-                    // Note: In the actual implementation, we would need a device reference
-                    // For now, we just validate
-                    if self.validate() {
-                        self.success_message = Some(
-                            "✓ Configuration is valid. In a full implementation, the buffer would be created here."
-                                .to_string(),
-                        );
+                if ui.button(tr("buffer-create")).clicked() {
+                    if let Some(device) = device {
+                        self.create_buffer(device);
+                    } else if self.validate() {
+                        self.success_message = Some(tr("buffer-valid"));
                     }
                 }
 
-                if ui.button("🔄 Reset").clicked() {
+                if ui.button(tr("buffer-reset")).clicked() {
                     *self = Self::new();
                 }
             });
+            self.gpu_errors.ui(ui, CREATE_BUFFER);
 
             ui.add_space(10.0);
 
             // Display validation errors or success messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("buffer-error", &[("error", error)]),
+                );
             }
 
             if let Some(success) = &self.success_message {
@@ -281,28 +647,37 @@ impl BufferPanel {
 
             ui.add_space(15.0);
 
+            // Import/Export
+            self.render_data_io(ui, device, queue);
+
+            ui.add_space(15.0);
+
             // Current Configuration Summary
             ui.group(|ui| {
-                ui.heading("Configuration Summary");
+                ui.heading(tr("buffer-summary-heading"));
                 ui.add_space(5.0);
 
                 self.update_descriptor();
 
-                ui.monospace(format!(
-                    "Label: {}",
-                    self.descriptor.label().unwrap_or("<none>")
+                let label = self
+                    .descriptor
+                    .label()
+                    .map_or_else(|| tr("buffer-summary-none"), str::to_string);
+                ui.monospace(tr_args("buffer-summary-label", &[("label", &label)]));
+                ui.monospace(tr_args(
+                    "buffer-summary-size",
+                    &[("size", &self.descriptor.size())],
                 ));
-                ui.monospace(format!("Size: {} bytes", self.descriptor.size()));
-                ui.monospace(format!(
-                    "Mapped at creation: {}",
-                    self.descriptor.mapped_at_creation()
+                ui.monospace(tr_args(
+                    "buffer-summary-mapped",
+                    &[("mapped", &self.descriptor.mapped_at_creation())],
                 ));
 
                 ui.add_space(5.0);
-                ui.label("Usage flags:");
+                ui.label(tr("buffer-summary-usage"));
                 let usage = self.descriptor.usage();
                 if usage.is_empty() {
-                    ui.monospace("  (none)");
+                    ui.monospace(format!("  {}", tr("buffer-summary-usage-none")));
                 } else {
                     if usage.contains(BufferUsages::VERTEX) {
                         ui.monospace("  • VERTEX");
@@ -349,19 +724,26 @@ impl BufferPanel {
                 if usage.contains(BufferUsages::VERTEX) || usage.contains(BufferUsages::UNIFORM) {
                     ui.group(|ui| {
                         ui.horizontal(|ui| {
-                            ui.heading("🎨 Live Preview");
-                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                if ui.small_button("✕").on_hover_text("Hide preview").clicked() {
-                                    self.show_preview = false;
-                                }
-                            });
+                            ui.heading(tr("buffer-preview-heading"));
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui
+                                        .small_button("✕")
+                                        .on_hover_text(tr("buffer-preview-hide"))
+                                        .clicked()
+                                    {
+                                        self.show_preview = false;
+                                    }
+                                },
+                            );
                         });
                         ui.add_space(5.0);
 
                         if usage.contains(BufferUsages::VERTEX) {
-                            ui.label("Preview shows how this vertex buffer could render a simple triangle mesh:");
+                            ui.label(tr("buffer-preview-vertex"));
                         } else if usage.contains(BufferUsages::UNIFORM) {
-                            ui.label("Preview shows animated uniform buffer values affecting rendering:");
+                            ui.label(tr("buffer-preview-uniform"));
                         }
 
                         ui.add_space(5.0);
@@ -401,7 +783,7 @@ impl BufferPanel {
                         } else if device.is_none() {
                             ui.colored_label(
                                 egui::Color32::YELLOW,
-                                "⚠ Preview requires GPU device to be initialized"
+                                tr("buffer-preview-needs-device"),
                             );
                         }
                     });
@@ -410,7 +792,7 @@ impl BufferPanel {
                 || self.descriptor.usage().contains(BufferUsages::UNIFORM)
             {
                 // Show button to enable preview
-                if ui.button("🎨 Show Live Preview").clicked() {
+                if ui.button(tr("buffer-preview-show")).clicked() {
                     self.show_preview = true;
                 }
             }
@@ -426,25 +808,27 @@ impl BufferPanel {
         queue: Option<&wgpu::Queue>,
     ) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("📐 Buffer Configuration");
-            ui.label("Configure and create GPU buffers with custom parameters.");
+            ui.heading(tr("buffer-heading"));
+            ui.label(tr("buffer-intro"));
             ui.add_space(10.0);
 
             // Buffer Label
             ui.group(|ui| {
-                ui.heading("Buffer Properties");
+                ui.heading(tr("buffer-properties"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("buffer_properties")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Label:")
-                            .webgpu_tooltip("Optional label for debugging and identification", Some("#dom-gpuobjectbase-label"));
+                        ui.label(tr("buffer-label")).webgpu_tooltip(
+                            &tr("buffer-label-tooltip"),
+                            Some("#dom-gpuobjectbase-label"),
+                        );
                         ui.text_edit_singleline(&mut self.label_input);
                         ui.end_row();
 
-                        property::BUFFER_SIZE.apply(ui.label("Size (bytes):"));
+                        property::BUFFER_SIZE.apply(ui.label(tr("buffer-size")));
                         ui.text_edit_singleline(&mut self.size_input);
                         ui.end_row();
                     });
@@ -454,8 +838,8 @@ impl BufferPanel {
 
             // Usage Flags
             ui.group(|ui| {
-                ui.heading("Usage Flags");
-                ui.label("Select how the buffer will be used (multiple flags can be selected):");
+                ui.heading(tr("buffer-usage-heading"));
+                ui.label(tr("buffer-usage-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("usage_flags")
@@ -478,7 +862,7 @@ impl BufferPanel {
                 ui.add_space(5.0);
                 ui.colored_label(
                     egui::Color32::from_rgb(200, 200, 100),
-                    "💡 Note: MAP_READ and MAP_WRITE cannot be used together"
+                    tr("buffer-usage-map-note"),
                 );
             });
 
@@ -486,34 +870,32 @@ impl BufferPanel {
 
             // Additional Options
             ui.group(|ui| {
-                ui.heading("Additional Options");
+                ui.heading(tr("buffer-options-heading"));
                 ui.add_space(5.0);
 
-                property::BUFFER_MAPPED_AT_CREATION.apply(
-                    ui.checkbox(&mut self.mapped_at_creation, "Mapped at creation")
-                );
+                property::BUFFER_MAPPED_AT_CREATION.apply(ui.checkbox(
+                    &mut self.mapped_at_creation,
+                    tr("buffer-mapped-at-creation"),
+                ));
             });
 
             ui.add_space(15.0);
 
             // Validation and Creation
             ui.horizontal(|ui| {
-                if ui.button("🔍 Validate").clicked() {
+                if ui.button(tr("buffer-validate")).clicked() {
                     self.validate();
                 }
 
-                if ui.button("✨ Create Buffer").clicked() {
+                if ui.button(tr("buffer-create")).clicked() {
                     // Note: In the actual implementation, we would need a device reference
                     // For now, we just validate
                     if self.validate() {
-                        self.success_message = Some(
-                            "✓ Configuration is valid. In a full implementation, the buffer would be created here."
-                                .to_string(),
-                        );
+                        self.success_message = Some(tr("buffer-valid"));
                     }
                 }
 
-                if ui.button("🔄 Reset").clicked() {
+                if ui.button(tr("buffer-reset")).clicked() {
                     *self = Self::new();
                 }
             });
@@ -522,7 +904,10 @@ impl BufferPanel {
 
             // Display validation errors or success messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("buffer-error", &[("error", error)]),
+                );
             }
 
             if let Some(success) = &self.success_message {
@@ -531,28 +916,37 @@ impl BufferPanel {
 
             ui.add_space(15.0);
 
+            // Import/Export
+            self.render_data_io(ui, device, queue);
+
+            ui.add_space(15.0);
+
             // Current Configuration Summary
             ui.group(|ui| {
-                ui.heading("Configuration Summary");
+                ui.heading(tr("buffer-summary-heading"));
                 ui.add_space(5.0);
 
                 self.update_descriptor();
 
-                ui.monospace(format!(
-                    "Label: {}",
-                    self.descriptor.label().unwrap_or("<none>")
+                let label = self
+                    .descriptor
+                    .label()
+                    .map_or_else(|| tr("buffer-summary-none"), str::to_string);
+                ui.monospace(tr_args("buffer-summary-label", &[("label", &label)]));
+                ui.monospace(tr_args(
+                    "buffer-summary-size",
+                    &[("size", &self.descriptor.size())],
                 ));
-                ui.monospace(format!("Size: {} bytes", self.descriptor.size()));
-                ui.monospace(format!(
-                    "Mapped at creation: {}",
-                    self.descriptor.mapped_at_creation()
+                ui.monospace(tr_args(
+                    "buffer-summary-mapped",
+                    &[("mapped", &self.descriptor.mapped_at_creation())],
                 ));
 
                 ui.add_space(5.0);
-                ui.label("Usage flags:");
+                ui.label(tr("buffer-summary-usage"));
                 let usage = self.descriptor.usage();
                 if usage.is_empty() {
-                    ui.monospace("  (none)");
+                    ui.monospace(format!("  {}", tr("buffer-summary-usage-none")));
                 } else {
                     if usage.contains(BufferUsages::VERTEX) {
                         ui.monospace("  • VERTEX");
@@ -592,12 +986,9 @@ impl BufferPanel {
             // Live Preview Section - Not available on WASM
             if self.show_preview {
                 ui.group(|ui| {
-                    ui.heading("🎨 Live Preview");
-                    ui.colored_label(
-                        egui::Color32::YELLOW,
-                        "⚠ Preview is not available in WASM builds"
-                    );
-                    if ui.button("Close Preview").clicked() {
+                    ui.heading(tr("buffer-preview-heading"));
+                    ui.colored_label(egui::Color32::YELLOW, tr("buffer-preview-wasm"));
+                    if ui.button(tr("buffer-preview-close")).clicked() {
                         self.show_preview = false;
                     }
                 });
@@ -605,7 +996,7 @@ impl BufferPanel {
                 || self.descriptor.usage().contains(BufferUsages::UNIFORM)
             {
                 // Show button to enable preview (but it won't work on WASM)
-                ui.label("💡 Preview is not available in WASM builds");
+                ui.label(tr("buffer-preview-wasm-note"));
             }
         });
     }
@@ -659,6 +1050,17 @@ impl BufferPanel {
     }
 }
 
+impl ResourceRecreate for BufferPanel {
+    fn recreate_resources(&mut self, device: &wgpu::Device, _queue: &wgpu::Queue) {
+        self.preview_state = None;
+        self.gpu_errors.clear_all();
+        // Recreate the buffer the user had created so it stays available
+        if self.created_buffer.take().is_some() {
+            self.create_buffer(device);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -818,6 +1220,57 @@ mod tests {
         assert!(usage.contains(BufferUsages::QUERY_RESOLVE));
     }
 
+    #[test]
+    fn test_csv_import_and_export_round_trip() {
+        let dir = std::env::temp_dir().join("wgpu_playground_buffer_panel_io");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("particles.csv");
+        std::fs::write(&input, "x,y,id\n0.5,1.5,1\n-2,3,2\n").unwrap();
+
+        let mut panel = BufferPanel::new();
+        panel.import_file(&input).unwrap();
+        assert!(panel.initial_data().is_none());
+        assert_eq!(
+            panel.column_types,
+            vec![ColumnType::F32, ColumnType::F32, ColumnType::U32]
+        );
+
+        assert_eq!(panel.confirm_csv_import().unwrap(), 2);
+        assert_eq!(panel.initial_data().unwrap().len(), 24);
+        assert_eq!(panel.size_input, "24");
+
+        let output = dir.join("particles_out.csv");
+        assert_eq!(panel.export_file(&output, None, None).unwrap(), 24);
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "0.5,1.5,1\n-2,3,2\n"
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_binary_import_is_padded() {
+        let dir = std::env::temp_dir().join("wgpu_playground_buffer_panel_bin");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("data.bin");
+        std::fs::write(&input, [1u8, 2, 3, 4, 5, 6]).unwrap();
+
+        let mut panel = BufferPanel::new();
+        panel.import_file(&input).unwrap();
+        assert_eq!(panel.initial_data().unwrap(), &[1, 2, 3, 4, 5, 6, 0, 0]);
+        assert_eq!(panel.size_input, "8");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_export_without_data_fails() {
+        let panel = BufferPanel::new();
+        let path = std::env::temp_dir().join("wgpu_playground_empty_export.bin");
+        assert!(panel.export_file(&path, None, None).is_err());
+    }
+
     // GUI Interaction Tests - Simulating User Workflows
 
     #[test]
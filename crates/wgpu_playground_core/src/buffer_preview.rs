@@ -5,6 +5,7 @@
 /// - Uniform buffers: Shows animated values
 use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
 use crate::buffer::BufferUsages;
+use crate::upload_belt;
 use wgpu::util::DeviceExt;
 
 /// Vertex structure for preview rendering
@@ -209,7 +210,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview_mask: None,
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         self.preview_vertex_buffer = Some(vertex_buffer);
@@ -341,7 +342,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview_mask: None,
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         self.preview_uniform_buffer = Some(uniform_buffer);
@@ -416,8 +417,12 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
                             time: self.time,
                             _padding: [0.0; 3],
                         };
-                        tracker.record(ApiCategory::Queue, "write_buffer");
-                        queue.write_buffer(uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+                        upload_belt::write_buffer(
+                            queue,
+                            uniform_buffer,
+                            0,
+                            bytemuck::cast_slice(&[uniforms]),
+                        );
                     }
 
                     // Render uniform buffer preview
@@ -434,8 +439,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
                 }
             }
 
-            tracker.record(ApiCategory::Queue, "submit");
-            queue.submit(Some(encoder.finish()));
+            upload_belt::submit(queue, Some(encoder.finish()));
         }
 
         self.render_texture_view.as_ref()
@@ -0,0 +1,483 @@
+//! Orbit and fly cameras for the 3D previews
+//!
+//! A [`Camera`] holds both controllers and switches between them without
+//! the view jumping:
+//!
+//! - Orbit circles a target point: drag to rotate, scroll to zoom
+//! - Fly moves freely: drag to look around, WASD to move, Q/E to go down
+//!   and up, Shift to speed up, scroll to change the speed
+//!
+//! Matrices are right-handed with Y up, column-major as WGSL expects, and
+//! project into WebGPU clip space with depth from 0 to 1.
+
+use crate::math_utils::{cross, look_at, mat4_mul, normalize, perspective, Mat4};
+
+/// Radians of rotation per dragged pixel
+const ROTATE_SPEED: f32 = 0.01;
+
+/// Fraction of the distance zoomed per scrolled point
+const ZOOM_SPEED: f32 = 0.002;
+
+/// Keeps the camera from flipping over the poles
+const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+/// Speed multiplier while Shift is held in fly mode
+const FAST_MULTIPLIER: f32 = 4.0;
+
+const UP: [f32; 3] = [0.0, 1.0, 0.0];
+
+/// Which controller drives the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraMode {
+    Orbit,
+    Fly,
+}
+
+/// Circles `target` at `distance`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitController {
+    pub target: [f32; 3],
+    pub distance: f32,
+    /// Angle around the Y axis; 0 places the camera on the +Z side
+    pub yaw: f32,
+    /// Elevation above the target's horizontal plane
+    pub pitch: f32,
+}
+
+impl OrbitController {
+    /// Position of the camera
+    pub fn eye(&self) -> [f32; 3] {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        [
+            self.target[0] + self.distance * cos_pitch * sin_yaw,
+            self.target[1] + self.distance * sin_pitch,
+            self.target[2] + self.distance * cos_pitch * cos_yaw,
+        ]
+    }
+
+    /// Rotate as if grabbing the scene and dragging it by `delta` pixels
+    pub fn rotate(&mut self, delta: [f32; 2]) {
+        self.yaw -= delta[0] * ROTATE_SPEED;
+        self.pitch = (self.pitch + delta[1] * ROTATE_SPEED).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Move closer for positive `scroll`, farther for negative
+    pub fn zoom(&mut self, scroll: f32) {
+        self.distance = (self.distance * (1.0 - scroll * ZOOM_SPEED).clamp(0.5, 2.0))
+            .clamp(Camera::MIN_DISTANCE, Camera::MAX_DISTANCE);
+    }
+}
+
+/// Moves freely through the scene
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlyController {
+    pub position: [f32; 3],
+    /// Heading; 0 looks down -Z, positive values turn right
+    pub yaw: f32,
+    /// Positive values look up
+    pub pitch: f32,
+    /// Units per second
+    pub speed: f32,
+}
+
+impl FlyController {
+    /// Unit vector the camera looks along
+    pub fn forward(&self) -> [f32; 3] {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        [cos_pitch * sin_yaw, sin_pitch, -cos_pitch * cos_yaw]
+    }
+
+    /// Turn by a mouse movement of `delta` pixels
+    pub fn look(&mut self, delta: [f32; 2]) {
+        self.yaw += delta[0] * ROTATE_SPEED;
+        self.pitch = (self.pitch - delta[1] * ROTATE_SPEED).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Move by `movement` (right, up, forward) at the fly speed for `dt` seconds
+    pub fn translate(&mut self, movement: [f32; 3], dt: f32) {
+        let forward = self.forward();
+        let right = normalize(cross(forward, UP));
+        let step = self.speed * dt;
+        for i in 0..3 {
+            self.position[i] +=
+                (right[i] * movement[0] + UP[i] * movement[1] + forward[i] * movement[2]) * step;
+        }
+    }
+}
+
+/// Input for one frame, independent of the UI toolkit
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CameraInput {
+    /// Mouse drag in pixels
+    pub drag: [f32; 2],
+    /// Scroll in points, positive away from the user
+    pub scroll: f32,
+    /// Held movement keys as (right, up, forward), each -1, 0 or 1
+    pub movement: [f32; 3],
+    /// Whether the speed-up modifier is held
+    pub fast: bool,
+    /// Seconds since the last frame
+    pub dt: f32,
+}
+
+impl CameraInput {
+    /// Read the input aimed at a preview image; `response` must sense drags
+    pub fn from_response(ui: &egui::Ui, response: &egui::Response) -> Self {
+        let mut input = Self::default();
+        if response.dragged() {
+            let delta = response.drag_delta();
+            input.drag = [delta.x, delta.y];
+        }
+        // Keys only steer the camera while the pointer is over the preview
+        // and no text field has focus
+        let keys_free = ui.ctx().memory(|memory| memory.focused().is_none());
+        if response.hovered() {
+            ui.input(|i| {
+                input.scroll = i.smooth_scroll_delta.y;
+                input.dt = i.stable_dt;
+                if keys_free {
+                    let axis = |positive, negative| {
+                        f32::from(u8::from(i.key_down(positive)))
+                            - f32::from(u8::from(i.key_down(negative)))
+                    };
+                    input.movement = [
+                        axis(egui::Key::D, egui::Key::A),
+                        axis(egui::Key::E, egui::Key::Q),
+                        axis(egui::Key::W, egui::Key::S),
+                    ];
+                    input.fast = i.modifiers.shift;
+                }
+            });
+        }
+        input
+    }
+
+    /// Whether a key is held that needs the UI to keep repainting
+    pub fn is_moving(&self) -> bool {
+        self.movement != [0.0; 3]
+    }
+}
+
+/// Camera shared by the 3D previews
+#[derive(Debug, Clone, PartialEq)]
+pub struct Camera {
+    pub mode: CameraMode,
+    pub orbit: OrbitController,
+    pub fly: FlyController,
+    /// Vertical field of view in radians
+    pub fov_y: f32,
+    pub near: f32,
+    pub far: f32,
+    /// Orbit restored by [`Camera::reset`]
+    home: OrbitController,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Camera {
+    pub const MIN_DISTANCE: f32 = 0.1;
+    pub const MAX_DISTANCE: f32 = 1000.0;
+
+    /// An orbit camera three units in front of the origin
+    pub fn new() -> Self {
+        let home = OrbitController {
+            target: [0.0; 3],
+            distance: 3.0,
+            yaw: 0.0,
+            pitch: 0.0,
+        };
+        let mut camera = Self {
+            mode: CameraMode::Orbit,
+            orbit: home,
+            fly: FlyController {
+                position: [0.0; 3],
+                yaw: 0.0,
+                pitch: 0.0,
+                speed: 2.0,
+            },
+            fov_y: 45.0_f32.to_radians(),
+            near: 0.1,
+            far: 100.0,
+            home,
+        };
+        camera.sync_fly_to_orbit();
+        camera
+    }
+
+    /// A camera orbiting `center` that fits a sphere of `radius` in view
+    pub fn framing(center: [f32; 3], radius: f32) -> Self {
+        let mut camera = Self::new();
+        camera.frame(center, radius);
+        camera
+    }
+
+    /// Orbit `center` from a distance that fits a sphere of `radius` in
+    /// view, looking slightly down, and make that the reset view
+    pub fn frame(&mut self, center: [f32; 3], radius: f32) {
+        let radius = radius.max(0.01);
+        let distance =
+            (radius / (self.fov_y / 2.0).sin() * 1.1).clamp(Self::MIN_DISTANCE, Self::MAX_DISTANCE);
+        self.home = OrbitController {
+            target: center,
+            distance,
+            yaw: 0.5,
+            pitch: 0.4,
+        };
+        // Leave room to zoom in and out without clipping the scene
+        self.near = distance * 0.01;
+        self.far = distance * 10.0 + radius;
+        self.fly.speed = radius;
+        self.reset();
+    }
+
+    /// Return to the orbit view set by [`Camera::frame`]
+    pub fn reset(&mut self) {
+        self.mode = CameraMode::Orbit;
+        self.orbit = self.home;
+        self.sync_fly_to_orbit();
+    }
+
+    /// Switch controllers, continuing from the current view
+    pub fn set_mode(&mut self, mode: CameraMode) {
+        if mode == self.mode {
+            return;
+        }
+        match mode {
+            CameraMode::Fly => self.sync_fly_to_orbit(),
+            CameraMode::Orbit => {
+                let forward = self.fly.forward();
+                self.orbit.yaw = -self.fly.yaw;
+                self.orbit.pitch = -self.fly.pitch;
+                self.orbit.target = std::array::from_fn(|i| {
+                    self.fly.position[i] + forward[i] * self.orbit.distance
+                });
+            }
+        }
+        self.mode = mode;
+    }
+
+    fn sync_fly_to_orbit(&mut self) {
+        self.fly.position = self.orbit.eye();
+        self.fly.yaw = -self.orbit.yaw;
+        self.fly.pitch = -self.orbit.pitch;
+    }
+
+    /// Position of the camera
+    pub fn eye(&self) -> [f32; 3] {
+        match self.mode {
+            CameraMode::Orbit => self.orbit.eye(),
+            CameraMode::Fly => self.fly.position,
+        }
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        match self.mode {
+            CameraMode::Orbit => look_at(self.orbit.eye(), self.orbit.target, UP),
+            CameraMode::Fly => {
+                let forward = self.fly.forward();
+                let center = std::array::from_fn(|i| self.fly.position[i] + forward[i]);
+                look_at(self.fly.position, center, UP)
+            }
+        }
+    }
+
+    pub fn projection_matrix(&self, aspect: f32) -> Mat4 {
+        perspective(self.fov_y, aspect, self.near, self.far)
+    }
+
+    /// Projection times view, ready for a `mat4x4<f32>` uniform
+    pub fn view_projection(&self, aspect: f32) -> Mat4 {
+        mat4_mul(&self.projection_matrix(aspect), &self.view_matrix())
+    }
+
+    /// Apply one frame of input to the active controller
+    pub fn apply_input(&mut self, input: &CameraInput) {
+        match self.mode {
+            CameraMode::Orbit => {
+                self.orbit.rotate(input.drag);
+                self.orbit.zoom(input.scroll);
+            }
+            CameraMode::Fly => {
+                self.fly.look(input.drag);
+                if input.scroll != 0.0 {
+                    self.fly.speed = (self.fly.speed
+                        * (1.0 + input.scroll * ZOOM_SPEED).clamp(0.5, 2.0))
+                    .clamp(0.01, 1000.0);
+                }
+                let multiplier = if input.fast { FAST_MULTIPLIER } else { 1.0 };
+                self.fly.translate(input.movement, input.dt * multiplier);
+            }
+        }
+    }
+
+    /// Steer the camera with the input aimed at a preview image that
+    /// senses drags
+    pub fn handle_input(&mut self, ui: &egui::Ui, response: &egui::Response) {
+        let input = CameraInput::from_response(ui, response);
+        self.apply_input(&input);
+        if input.is_moving() {
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Short usage hint for the active controller
+    pub fn help_text(&self) -> &'static str {
+        match self.mode {
+            CameraMode::Orbit => "💡 Drag to rotate, scroll to zoom",
+            CameraMode::Fly => "💡 Drag to look, WASD to move, Q/E down/up, Shift for speed",
+        }
+    }
+
+    /// Mode switch, reset button and projection settings
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Camera:");
+            let mut mode = self.mode;
+            ui.selectable_value(&mut mode, CameraMode::Orbit, "🔄 Orbit");
+            ui.selectable_value(&mut mode, CameraMode::Fly, "✈ Fly");
+            self.set_mode(mode);
+            if ui.button("Reset").clicked() {
+                self.reset();
+            }
+        });
+        let mut fov = self.fov_y.to_degrees();
+        if ui
+            .add(egui::Slider::new(&mut fov, 20.0..=120.0).text("Field of view (°)"))
+            .changed()
+        {
+            self.fov_y = fov.to_radians();
+        }
+        if self.mode == CameraMode::Fly {
+            ui.add(
+                egui::Slider::new(&mut self.fly.speed, 0.01..=1000.0)
+                    .logarithmic(true)
+                    .text("Fly speed"),
+            );
+        }
+        ui.label(
+            egui::RichText::new(self.help_text())
+                .color(egui::Color32::GRAY)
+                .italics(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_utils::transform_point;
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_default_camera_sees_origin_in_the_middle() {
+        let camera = Camera::new();
+        assert_close(camera.eye(), [0.0, 0.0, 3.0]);
+        let clip = transform_point(&camera.view_projection(1.0), [0.0; 3]);
+        assert!(clip[0].abs() < 1e-6 && clip[1].abs() < 1e-6);
+        assert!(clip[2] > 0.0 && clip[2] < 1.0);
+    }
+
+    #[test]
+    fn test_orbit_rotation_keeps_distance_and_clamps_pitch() {
+        let mut camera = Camera::new();
+        camera.apply_input(&CameraInput {
+            drag: [120.0, 10_000.0],
+            ..Default::default()
+        });
+        let eye = camera.eye();
+        let distance = (eye[0] * eye[0] + eye[1] * eye[1] + eye[2] * eye[2]).sqrt();
+        assert!((distance - 3.0).abs() < 1e-4);
+        assert_eq!(camera.orbit.pitch, MAX_PITCH);
+        assert!(eye[0] < 0.0, "dragging right swings the camera left");
+    }
+
+    #[test]
+    fn test_orbit_zoom_is_clamped() {
+        let mut camera = Camera::new();
+        camera.apply_input(&CameraInput {
+            scroll: 100.0,
+            ..Default::default()
+        });
+        assert!(camera.orbit.distance < 3.0);
+        for _ in 0..100 {
+            camera.apply_input(&CameraInput {
+                scroll: 400.0,
+                ..Default::default()
+            });
+        }
+        assert_eq!(camera.orbit.distance, Camera::MIN_DISTANCE);
+    }
+
+    #[test]
+    fn test_switching_modes_keeps_the_view() {
+        let mut camera = Camera::framing([1.0, 2.0, 3.0], 2.0);
+        camera.orbit.rotate([40.0, -25.0]);
+        let view = camera.view_matrix();
+
+        camera.set_mode(CameraMode::Fly);
+        assert_close(camera.eye(), camera.orbit.eye());
+        let fly_view = camera.view_matrix();
+        for column in 0..4 {
+            let a = [view[column][0], view[column][1], view[column][2]];
+            let b = [
+                fly_view[column][0],
+                fly_view[column][1],
+                fly_view[column][2],
+            ];
+            assert_close(a, b);
+        }
+
+        camera.set_mode(CameraMode::Orbit);
+        assert_close(camera.orbit.target, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_fly_moves_along_view_direction() {
+        let mut camera = Camera::new();
+        camera.set_mode(CameraMode::Fly);
+        let start = camera.eye();
+        camera.apply_input(&CameraInput {
+            movement: [0.0, 0.0, 1.0],
+            dt: 0.5,
+            ..Default::default()
+        });
+        // Looking at the origin from +Z, forward is -Z at 2 units per second
+        assert_close(camera.eye(), [start[0], start[1], start[2] - 1.0]);
+
+        camera.apply_input(&CameraInput {
+            movement: [1.0, 1.0, 0.0],
+            fast: true,
+            dt: 0.25,
+            ..Default::default()
+        });
+        assert_close(camera.eye(), [2.0, 2.0, start[2] - 1.0]);
+    }
+
+    #[test]
+    fn test_frame_fits_sphere_and_reset_returns_home() {
+        let mut camera = Camera::framing([0.0; 3], 10.0);
+        let projection = camera.view_projection(1.0);
+        for point in [[10.0, 0.0, 0.0], [0.0, -10.0, 0.0], [0.0, 0.0, 10.0]] {
+            let clip = transform_point(&projection, point);
+            assert!(clip.iter().all(|c| c.abs() <= 1.0), "{:?}", clip);
+            assert!(clip[2] >= 0.0);
+        }
+
+        camera.set_mode(CameraMode::Fly);
+        camera.fly.translate([0.0, 0.0, 1.0], 3.0);
+        camera.reset();
+        assert_eq!(camera.mode, CameraMode::Orbit);
+        assert_eq!(camera, Camera::framing([0.0; 3], 10.0));
+    }
+}
@@ -0,0 +1,837 @@
+//! Device capability snapshots for offline validation
+//!
+//! A [`CapabilitySnapshot`] records an adapter's features, limits and per-format
+//! capabilities as JSON. Loading a snapshot as a [`VirtualDevice`] allows
+//! descriptors to be validated against another machine's GPU without having
+//! access to the hardware, e.g. to check whether a project would run on a
+//! colleague's laptop.
+//!
+//! # Example
+//!
+//! ```
+//! use wgpu_playground_core::capability_snapshot::{CapabilitySnapshot, VirtualDevice};
+//!
+//! let snapshot = CapabilitySnapshot::from_parts(
+//!     "Test GPU",
+//!     "Vulkan",
+//!     wgpu::Features::empty(),
+//!     &wgpu::Limits::default(),
+//! );
+//! let json = snapshot.to_json().unwrap();
+//!
+//! let device = VirtualDevice::new(CapabilitySnapshot::from_json(&json).unwrap());
+//! let issues = device.validate_buffer(1024, wgpu::BufferUsages::UNIFORM);
+//! assert!(issues.is_empty());
+//! ```
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use wgpu::{BufferUsages, Features, Limits, TextureDimension, TextureFormat, TextureUsages};
+
+/// Current snapshot file format version
+pub const SNAPSHOT_VERSION: &str = "1.0";
+
+/// Texture formats probed when capturing a snapshot
+pub const PROBED_FORMATS: &[TextureFormat] = &[
+    TextureFormat::R8Unorm,
+    TextureFormat::R8Uint,
+    TextureFormat::R16Float,
+    TextureFormat::R32Float,
+    TextureFormat::R32Uint,
+    TextureFormat::Rg8Unorm,
+    TextureFormat::Rg16Float,
+    TextureFormat::Rg32Float,
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::Rgb10a2Unorm,
+    TextureFormat::Rg11b10Ufloat,
+    TextureFormat::Rgba16Float,
+    TextureFormat::Rgba32Float,
+    TextureFormat::Rgba32Uint,
+    TextureFormat::Depth16Unorm,
+    TextureFormat::Depth24Plus,
+    TextureFormat::Depth24PlusStencil8,
+    TextureFormat::Depth32Float,
+    TextureFormat::Depth32FloatStencil8,
+];
+
+/// Serializable copy of the WebGPU core limits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LimitsSnapshot {
+    pub max_texture_dimension_1d: u32,
+    pub max_texture_dimension_2d: u32,
+    pub max_texture_dimension_3d: u32,
+    pub max_texture_array_layers: u32,
+    pub max_bind_groups: u32,
+    pub max_bindings_per_bind_group: u32,
+    pub max_dynamic_uniform_buffers_per_pipeline_layout: u32,
+    pub max_dynamic_storage_buffers_per_pipeline_layout: u32,
+    pub max_sampled_textures_per_shader_stage: u32,
+    pub max_samplers_per_shader_stage: u32,
+    pub max_storage_buffers_per_shader_stage: u32,
+    pub max_storage_textures_per_shader_stage: u32,
+    pub max_uniform_buffers_per_shader_stage: u32,
+    pub max_uniform_buffer_binding_size: u64,
+    pub max_storage_buffer_binding_size: u64,
+    pub max_vertex_buffers: u32,
+    pub max_buffer_size: u64,
+    pub max_vertex_attributes: u32,
+    pub max_vertex_buffer_array_stride: u32,
+    pub min_uniform_buffer_offset_alignment: u32,
+    pub min_storage_buffer_offset_alignment: u32,
+    pub max_inter_stage_shader_variables: u32,
+    pub max_color_attachments: u32,
+    pub max_color_attachment_bytes_per_sample: u32,
+    pub max_compute_workgroup_storage_size: u32,
+    pub max_compute_invocations_per_workgroup: u32,
+    pub max_compute_workgroup_size_x: u32,
+    pub max_compute_workgroup_size_y: u32,
+    pub max_compute_workgroup_size_z: u32,
+    pub max_compute_workgroups_per_dimension: u32,
+}
+
+impl From<&Limits> for LimitsSnapshot {
+    fn from(limits: &Limits) -> Self {
+        Self {
+            max_texture_dimension_1d: limits.max_texture_dimension_1d,
+            max_texture_dimension_2d: limits.max_texture_dimension_2d,
+            max_texture_dimension_3d: limits.max_texture_dimension_3d,
+            max_texture_array_layers: limits.max_texture_array_layers,
+            max_bind_groups: limits.max_bind_groups,
+            max_bindings_per_bind_group: limits.max_bindings_per_bind_group,
+            max_dynamic_uniform_buffers_per_pipeline_layout: limits
+                .max_dynamic_uniform_buffers_per_pipeline_layout,
+            max_dynamic_storage_buffers_per_pipeline_layout: limits
+                .max_dynamic_storage_buffers_per_pipeline_layout,
+            max_sampled_textures_per_shader_stage: limits.max_sampled_textures_per_shader_stage,
+            max_samplers_per_shader_stage: limits.max_samplers_per_shader_stage,
+            max_storage_buffers_per_shader_stage: limits.max_storage_buffers_per_shader_stage,
+            max_storage_textures_per_shader_stage: limits.max_storage_textures_per_shader_stage,
+            max_uniform_buffers_per_shader_stage: limits.max_uniform_buffers_per_shader_stage,
+            max_uniform_buffer_binding_size: limits.max_uniform_buffer_binding_size,
+            max_storage_buffer_binding_size: limits.max_storage_buffer_binding_size,
+            max_vertex_buffers: limits.max_vertex_buffers,
+            max_buffer_size: limits.max_buffer_size,
+            max_vertex_attributes: limits.max_vertex_attributes,
+            max_vertex_buffer_array_stride: limits.max_vertex_buffer_array_stride,
+            min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment,
+            min_storage_buffer_offset_alignment: limits.min_storage_buffer_offset_alignment,
+            max_inter_stage_shader_variables: limits.max_inter_stage_shader_variables,
+            max_color_attachments: limits.max_color_attachments,
+            max_color_attachment_bytes_per_sample: limits.max_color_attachment_bytes_per_sample,
+            max_compute_workgroup_storage_size: limits.max_compute_workgroup_storage_size,
+            max_compute_invocations_per_workgroup: limits.max_compute_invocations_per_workgroup,
+            max_compute_workgroup_size_x: limits.max_compute_workgroup_size_x,
+            max_compute_workgroup_size_y: limits.max_compute_workgroup_size_y,
+            max_compute_workgroup_size_z: limits.max_compute_workgroup_size_z,
+            max_compute_workgroups_per_dimension: limits.max_compute_workgroups_per_dimension,
+        }
+    }
+}
+
+impl LimitsSnapshot {
+    /// Convert back into `wgpu::Limits`
+    ///
+    /// Limits that are not part of the snapshot keep their wgpu defaults.
+    pub fn to_wgpu(&self) -> Limits {
+        Limits {
+            max_texture_dimension_1d: self.max_texture_dimension_1d,
+            max_texture_dimension_2d: self.max_texture_dimension_2d,
+            max_texture_dimension_3d: self.max_texture_dimension_3d,
+            max_texture_array_layers: self.max_texture_array_layers,
+            max_bind_groups: self.max_bind_groups,
+            max_bindings_per_bind_group: self.max_bindings_per_bind_group,
+            max_dynamic_uniform_buffers_per_pipeline_layout: self
+                .max_dynamic_uniform_buffers_per_pipeline_layout,
+            max_dynamic_storage_buffers_per_pipeline_layout: self
+                .max_dynamic_storage_buffers_per_pipeline_layout,
+            max_sampled_textures_per_shader_stage: self.max_sampled_textures_per_shader_stage,
+            max_samplers_per_shader_stage: self.max_samplers_per_shader_stage,
+            max_storage_buffers_per_shader_stage: self.max_storage_buffers_per_shader_stage,
+            max_storage_textures_per_shader_stage: self.max_storage_textures_per_shader_stage,
+            max_uniform_buffers_per_shader_stage: self.max_uniform_buffers_per_shader_stage,
+            max_uniform_buffer_binding_size: self.max_uniform_buffer_binding_size,
+            max_storage_buffer_binding_size: self.max_storage_buffer_binding_size,
+            max_vertex_buffers: self.max_vertex_buffers,
+            max_buffer_size: self.max_buffer_size,
+            max_vertex_attributes: self.max_vertex_attributes,
+            max_vertex_buffer_array_stride: self.max_vertex_buffer_array_stride,
+            min_uniform_buffer_offset_alignment: self.min_uniform_buffer_offset_alignment,
+            min_storage_buffer_offset_alignment: self.min_storage_buffer_offset_alignment,
+            max_inter_stage_shader_variables: self.max_inter_stage_shader_variables,
+            max_color_attachments: self.max_color_attachments,
+            max_color_attachment_bytes_per_sample: self.max_color_attachment_bytes_per_sample,
+            max_compute_workgroup_storage_size: self.max_compute_workgroup_storage_size,
+            max_compute_invocations_per_workgroup: self.max_compute_invocations_per_workgroup,
+            max_compute_workgroup_size_x: self.max_compute_workgroup_size_x,
+            max_compute_workgroup_size_y: self.max_compute_workgroup_size_y,
+            max_compute_workgroup_size_z: self.max_compute_workgroup_size_z,
+            max_compute_workgroups_per_dimension: self.max_compute_workgroups_per_dimension,
+            ..Limits::default()
+        }
+    }
+}
+
+/// Capabilities of a single texture format on the captured adapter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormatCapability {
+    /// Format name as printed by `Debug` (e.g. "Rgba8Unorm")
+    pub format: String,
+    /// Allowed `TextureUsages` flag names
+    pub allowed_usages: Vec<String>,
+    /// `TextureFormatFeatureFlags` flag names
+    pub flags: Vec<String>,
+}
+
+impl FormatCapability {
+    /// Parse the allowed usages back into `wgpu::TextureUsages`
+    pub fn usages(&self) -> TextureUsages {
+        self.allowed_usages
+            .iter()
+            .filter_map(|name| TextureUsages::from_name(name))
+            .fold(TextureUsages::empty(), |acc, u| acc | u)
+    }
+
+    /// Check whether a `TextureFormatFeatureFlags` flag is present by name
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|f| f == name)
+    }
+}
+
+/// A JSON-serializable record of an adapter's capabilities
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilitySnapshot {
+    /// Snapshot format version
+    pub version: String,
+    /// Adapter name
+    pub adapter_name: String,
+    /// Backend name (e.g. "Vulkan")
+    pub backend: String,
+    /// Device type (e.g. "DiscreteGpu")
+    #[serde(default)]
+    pub device_type: String,
+    /// Driver description
+    #[serde(default)]
+    pub driver: String,
+    /// When the snapshot was captured (RFC 3339)
+    #[serde(default)]
+    pub captured_at: String,
+    /// Enabled feature flag names (e.g. "TIMESTAMP_QUERY")
+    pub features: Vec<String>,
+    /// Supported limits
+    pub limits: LimitsSnapshot,
+    /// Per-format capabilities
+    #[serde(default)]
+    pub formats: Vec<FormatCapability>,
+}
+
+impl CapabilitySnapshot {
+    /// Build a snapshot from explicit features and limits
+    ///
+    /// No format capabilities are recorded, so format checks on the resulting
+    /// virtual device fall back to the format's guaranteed WebGPU capabilities.
+    pub fn from_parts(
+        adapter_name: impl Into<String>,
+        backend: impl Into<String>,
+        features: Features,
+        limits: &Limits,
+    ) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION.to_string(),
+            adapter_name: adapter_name.into(),
+            backend: backend.into(),
+            device_type: String::new(),
+            driver: String::new(),
+            captured_at: chrono::Utc::now().to_rfc3339(),
+            features: features
+                .iter_names()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            limits: LimitsSnapshot::from(limits),
+            formats: Vec::new(),
+        }
+    }
+
+    /// Capture the capabilities of an adapter
+    pub fn from_adapter(adapter: &wgpu::Adapter) -> Self {
+        let info = adapter.get_info();
+        let mut snapshot = Self::from_parts(
+            info.name.clone(),
+            crate::adapter::backend_to_str(&info.backend),
+            adapter.features(),
+            &adapter.limits(),
+        );
+        snapshot.device_type = format!("{:?}", info.device_type);
+        snapshot.driver = format!("{} {}", info.driver, info.driver_info)
+            .trim()
+            .to_string();
+        snapshot.formats = PROBED_FORMATS
+            .iter()
+            .map(|&format| {
+                let features = adapter.get_texture_format_features(format);
+                FormatCapability {
+                    format: format!("{:?}", format),
+                    allowed_usages: features
+                        .allowed_usages
+                        .iter_names()
+                        .map(|(name, _)| name.to_string())
+                        .collect(),
+                    flags: features
+                        .flags
+                        .iter_names()
+                        .map(|(name, _)| name.to_string())
+                        .collect(),
+                }
+            })
+            .collect();
+        snapshot
+    }
+
+    /// The recorded features as `wgpu::Features`
+    ///
+    /// Unknown feature names (e.g. from a newer wgpu version) are ignored.
+    pub fn wgpu_features(&self) -> Features {
+        self.features
+            .iter()
+            .filter_map(|name| Features::from_name(name))
+            .fold(Features::empty(), |acc, f| acc | f)
+    }
+
+    /// Look up the recorded capabilities of a texture format
+    pub fn format_capability(&self, format: TextureFormat) -> Option<&FormatCapability> {
+        let name = format!("{:?}", format);
+        self.formats.iter().find(|f| f.format == name)
+    }
+
+    /// Serialize to a pretty-printed JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Save the snapshot to a JSON file
+    pub fn save_to_file(&self, path: &Path) -> Result<(), std::io::Error> {
+        let json = self.to_json().map_err(std::io::Error::other)?;
+        std::fs::write(path, json)?;
+        log::info!("Saved capability snapshot to {:?}", path);
+        Ok(())
+    }
+
+    /// Load a snapshot from a JSON file
+    pub fn load_from_file(path: &Path) -> Result<Self, std::io::Error> {
+        let json = std::fs::read_to_string(path)?;
+        let snapshot = Self::from_json(&json).map_err(std::io::Error::other)?;
+        log::info!("Loaded capability snapshot from {:?}", path);
+        Ok(snapshot)
+    }
+}
+
+/// A problem found while validating against a virtual device
+#[derive(Debug, Clone, PartialEq)]
+pub enum CapabilityIssue {
+    /// A required feature is not supported
+    MissingFeature(String),
+    /// A limit is exceeded
+    LimitExceeded {
+        limit: String,
+        requested: u64,
+        allowed: u64,
+    },
+    /// A texture format does not support the requested usage or sample count
+    UnsupportedFormat { format: String, reason: String },
+    /// The descriptor is invalid regardless of the device
+    InvalidDescriptor(String),
+}
+
+impl fmt::Display for CapabilityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFeature(name) => write!(f, "Missing feature: {}", name),
+            Self::LimitExceeded {
+                limit,
+                requested,
+                allowed,
+            } => write!(
+                f,
+                "Limit exceeded: {} requested {}, allowed {}",
+                limit, requested, allowed
+            ),
+            Self::UnsupportedFormat { format, reason } => {
+                write!(f, "Unsupported format {}: {}", format, reason)
+            }
+            Self::InvalidDescriptor(msg) => write!(f, "Invalid descriptor: {}", msg),
+        }
+    }
+}
+
+/// A descriptor configured in the playground, to check against a virtual device
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectDescriptor {
+    /// A buffer from the buffer panel
+    Buffer {
+        label: String,
+        size: u64,
+        usage: BufferUsages,
+    },
+    /// A texture from the texture panel
+    Texture {
+        label: String,
+        format: TextureFormat,
+        dimension: TextureDimension,
+        size: wgpu::Extent3d,
+        usage: TextureUsages,
+        sample_count: u32,
+    },
+    /// The workgroup size of a compute entry point
+    Workgroup { entry_point: String, size: [u32; 3] },
+}
+
+impl fmt::Display for ProjectDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Buffer { label, size, .. } => write!(f, "Buffer '{}' ({} bytes)", label, size),
+            Self::Texture {
+                label,
+                format,
+                size,
+                ..
+            } => write!(
+                f,
+                "Texture '{}' ({}x{}x{} {:?})",
+                label, size.width, size.height, size.depth_or_array_layers, format
+            ),
+            Self::Workgroup { entry_point, size } => write!(
+                f,
+                "Workgroup of {} ({}x{}x{})",
+                entry_point, size[0], size[1], size[2]
+            ),
+        }
+    }
+}
+
+/// A device backed by a capability snapshot instead of real hardware
+///
+/// Only performs capability checks (features, limits, format support); full
+/// WebGPU validation still requires a real device.
+#[derive(Debug, Clone)]
+pub struct VirtualDevice {
+    snapshot: CapabilitySnapshot,
+    features: Features,
+    limits: Limits,
+}
+
+impl VirtualDevice {
+    /// Create a virtual device from a snapshot
+    pub fn new(snapshot: CapabilitySnapshot) -> Self {
+        let features = snapshot.wgpu_features();
+        let limits = snapshot.limits.to_wgpu();
+        Self {
+            snapshot,
+            features,
+            limits,
+        }
+    }
+
+    /// Get the underlying snapshot
+    pub fn snapshot(&self) -> &CapabilitySnapshot {
+        &self.snapshot
+    }
+
+    /// Get the features of the virtual device
+    pub fn features(&self) -> Features {
+        self.features
+    }
+
+    /// Get the limits of the virtual device
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Check that all required features are available
+    pub fn check_features(&self, required: Features) -> Vec<CapabilityIssue> {
+        let missing = required - self.features;
+        missing
+            .iter_names()
+            .map(|(name, _)| CapabilityIssue::MissingFeature(name.to_string()))
+            .collect()
+    }
+
+    /// Check that the required limits fit within the virtual device limits
+    pub fn check_limits(&self, required: &Limits) -> Vec<CapabilityIssue> {
+        let mut issues = Vec::new();
+        required.check_limits_with_fail_fn(&self.limits, false, |name, requested, allowed| {
+            issues.push(CapabilityIssue::LimitExceeded {
+                limit: name.to_string(),
+                requested,
+                allowed,
+            });
+        });
+        issues
+    }
+
+    /// Validate a buffer allocation
+    pub fn validate_buffer(&self, size: u64, usage: BufferUsages) -> Vec<CapabilityIssue> {
+        let mut issues = Vec::new();
+        if usage.is_empty() {
+            issues.push(CapabilityIssue::InvalidDescriptor(
+                "Buffer usage must not be empty".to_string(),
+            ));
+        }
+        if size > self.limits.max_buffer_size {
+            issues.push(CapabilityIssue::LimitExceeded {
+                limit: "max_buffer_size".to_string(),
+                requested: size,
+                allowed: self.limits.max_buffer_size,
+            });
+        }
+        if usage.contains(BufferUsages::UNIFORM)
+            && size > self.limits.max_uniform_buffer_binding_size
+        {
+            issues.push(CapabilityIssue::LimitExceeded {
+                limit: "max_uniform_buffer_binding_size".to_string(),
+                requested: size,
+                allowed: self.limits.max_uniform_buffer_binding_size,
+            });
+        }
+        if usage.contains(BufferUsages::STORAGE)
+            && size > self.limits.max_storage_buffer_binding_size
+        {
+            issues.push(CapabilityIssue::LimitExceeded {
+                limit: "max_storage_buffer_binding_size".to_string(),
+                requested: size,
+                allowed: self.limits.max_storage_buffer_binding_size,
+            });
+        }
+        issues
+    }
+
+    /// Validate a texture allocation
+    pub fn validate_texture(
+        &self,
+        format: TextureFormat,
+        dimension: TextureDimension,
+        size: wgpu::Extent3d,
+        usage: TextureUsages,
+        sample_count: u32,
+    ) -> Vec<CapabilityIssue> {
+        let mut issues = Vec::new();
+
+        let (max_extent, extent_name, extent) = match dimension {
+            TextureDimension::D1 => (
+                self.limits.max_texture_dimension_1d,
+                "max_texture_dimension_1d",
+                size.width,
+            ),
+            TextureDimension::D2 => (
+                self.limits.max_texture_dimension_2d,
+                "max_texture_dimension_2d",
+                size.width.max(size.height),
+            ),
+            TextureDimension::D3 => (
+                self.limits.max_texture_dimension_3d,
+                "max_texture_dimension_3d",
+                size.width.max(size.height).max(size.depth_or_array_layers),
+            ),
+        };
+        if extent > max_extent {
+            issues.push(CapabilityIssue::LimitExceeded {
+                limit: extent_name.to_string(),
+                requested: extent as u64,
+                allowed: max_extent as u64,
+            });
+        }
+        if dimension == TextureDimension::D2
+            && size.depth_or_array_layers > self.limits.max_texture_array_layers
+        {
+            issues.push(CapabilityIssue::LimitExceeded {
+                limit: "max_texture_array_layers".to_string(),
+                requested: size.depth_or_array_layers as u64,
+                allowed: self.limits.max_texture_array_layers as u64,
+            });
+        }
+
+        issues.extend(self.check_features(format.required_features()));
+
+        let format_name = format!("{:?}", format);
+        match self.snapshot.format_capability(format) {
+            Some(capability) => {
+                let unsupported = usage - capability.usages();
+                if !unsupported.is_empty() {
+                    issues.push(CapabilityIssue::UnsupportedFormat {
+                        format: format_name.clone(),
+                        reason: format!("usage {:?} not supported", unsupported),
+                    });
+                }
+                if sample_count > 1
+                    && !capability.has_flag(&format!("MULTISAMPLE_X{}", sample_count))
+                {
+                    issues.push(CapabilityIssue::UnsupportedFormat {
+                        format: format_name,
+                        reason: format!("{}x multisampling not supported", sample_count),
+                    });
+                }
+            }
+            None => {
+                let guaranteed = format.guaranteed_format_features(self.features);
+                let unsupported = usage - guaranteed.allowed_usages;
+                if !unsupported.is_empty() {
+                    issues.push(CapabilityIssue::UnsupportedFormat {
+                        format: format_name,
+                        reason: format!("usage {:?} not guaranteed", unsupported),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Validate a compute workgroup size declared by a shader
+    pub fn validate_workgroup_size(&self, size: [u32; 3]) -> Vec<CapabilityIssue> {
+        let mut issues = Vec::new();
+        let per_axis = [
+            (
+                "max_compute_workgroup_size_x",
+                self.limits.max_compute_workgroup_size_x,
+            ),
+            (
+                "max_compute_workgroup_size_y",
+                self.limits.max_compute_workgroup_size_y,
+            ),
+            (
+                "max_compute_workgroup_size_z",
+                self.limits.max_compute_workgroup_size_z,
+            ),
+        ];
+        for (value, (name, max)) in size.iter().zip(per_axis) {
+            if *value > max {
+                issues.push(CapabilityIssue::LimitExceeded {
+                    limit: name.to_string(),
+                    requested: *value as u64,
+                    allowed: max as u64,
+                });
+            }
+        }
+        let invocations = size.iter().map(|&v| v as u64).product::<u64>();
+        if invocations > self.limits.max_compute_invocations_per_workgroup as u64 {
+            issues.push(CapabilityIssue::LimitExceeded {
+                limit: "max_compute_invocations_per_workgroup".to_string(),
+                requested: invocations,
+                allowed: self.limits.max_compute_invocations_per_workgroup as u64,
+            });
+        }
+        issues
+    }
+
+    /// Validate a descriptor with the matching `validate_*` check
+    pub fn validate_descriptor(&self, descriptor: &ProjectDescriptor) -> Vec<CapabilityIssue> {
+        match descriptor {
+            ProjectDescriptor::Buffer { size, usage, .. } => self.validate_buffer(*size, *usage),
+            ProjectDescriptor::Texture {
+                format,
+                dimension,
+                size,
+                usage,
+                sample_count,
+                ..
+            } => self.validate_texture(*format, *dimension, *size, *usage, *sample_count),
+            ProjectDescriptor::Workgroup { size, .. } => self.validate_workgroup_size(*size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_snapshot() -> CapabilitySnapshot {
+        CapabilitySnapshot::from_parts(
+            "Test GPU",
+            "Vulkan",
+            Features::TIMESTAMP_QUERY,
+            &Limits::default(),
+        )
+    }
+
+    #[test]
+    fn test_limits_roundtrip() {
+        let limits = Limits::default();
+        let snapshot = LimitsSnapshot::from(&limits);
+        let restored = snapshot.to_wgpu();
+        assert_eq!(
+            restored.max_texture_dimension_2d,
+            limits.max_texture_dimension_2d
+        );
+        assert_eq!(restored.max_buffer_size, limits.max_buffer_size);
+        assert_eq!(restored.max_bind_groups, limits.max_bind_groups);
+    }
+
+    #[test]
+    fn test_snapshot_json_roundtrip() {
+        let snapshot = default_snapshot();
+        let json = snapshot.to_json().unwrap();
+        assert!(json.contains("TIMESTAMP_QUERY"));
+        let restored = CapabilitySnapshot::from_json(&json).unwrap();
+        assert_eq!(restored, snapshot);
+        assert!(restored.wgpu_features().contains(Features::TIMESTAMP_QUERY));
+    }
+
+    #[test]
+    fn test_unknown_features_ignored() {
+        let mut snapshot = default_snapshot();
+        snapshot.features.push("NOT_A_REAL_FEATURE".to_string());
+        assert_eq!(snapshot.wgpu_features(), Features::TIMESTAMP_QUERY);
+    }
+
+    #[test]
+    fn test_check_features() {
+        let device = VirtualDevice::new(default_snapshot());
+        assert!(device.check_features(Features::TIMESTAMP_QUERY).is_empty());
+        let issues = device.check_features(Features::SHADER_F16);
+        assert_eq!(
+            issues,
+            vec![CapabilityIssue::MissingFeature("SHADER_F16".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let device = VirtualDevice::new(default_snapshot());
+        assert!(device.check_limits(&Limits::default()).is_empty());
+
+        let required = Limits {
+            max_bind_groups: 64,
+            ..Limits::default()
+        };
+        let issues = device.check_limits(&required);
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            CapabilityIssue::LimitExceeded { limit, .. } if limit == "max_bind_groups"
+        ));
+    }
+
+    #[test]
+    fn test_validate_buffer() {
+        let device = VirtualDevice::new(default_snapshot());
+        assert!(device.validate_buffer(256, BufferUsages::VERTEX).is_empty());
+        assert_eq!(device.validate_buffer(256, BufferUsages::empty()).len(), 1);
+
+        let too_big = device.limits().max_uniform_buffer_binding_size + 1;
+        let issues = device.validate_buffer(too_big, BufferUsages::UNIFORM);
+        assert!(issues.iter().any(|i| matches!(
+            i,
+            CapabilityIssue::LimitExceeded { limit, .. } if limit == "max_uniform_buffer_binding_size"
+        )));
+    }
+
+    #[test]
+    fn test_validate_texture_dimensions() {
+        let device = VirtualDevice::new(default_snapshot());
+        let max = device.limits().max_texture_dimension_2d;
+        let ok = device.validate_texture(
+            TextureFormat::Rgba8Unorm,
+            TextureDimension::D2,
+            wgpu::Extent3d {
+                width: max,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureUsages::TEXTURE_BINDING,
+            1,
+        );
+        assert!(ok.is_empty());
+
+        let too_big = device.validate_texture(
+            TextureFormat::Rgba8Unorm,
+            TextureDimension::D2,
+            wgpu::Extent3d {
+                width: max + 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureUsages::TEXTURE_BINDING,
+            1,
+        );
+        assert_eq!(too_big.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_texture_recorded_format() {
+        let mut snapshot = default_snapshot();
+        snapshot.formats.push(FormatCapability {
+            format: "Rgba8Unorm".to_string(),
+            allowed_usages: vec!["TEXTURE_BINDING".to_string(), "COPY_DST".to_string()],
+            flags: vec!["FILTERABLE".to_string()],
+        });
+        let device = VirtualDevice::new(snapshot);
+        let size = wgpu::Extent3d {
+            width: 16,
+            height: 16,
+            depth_or_array_layers: 1,
+        };
+
+        let issues = device.validate_texture(
+            TextureFormat::Rgba8Unorm,
+            TextureDimension::D2,
+            size,
+            TextureUsages::STORAGE_BINDING,
+            1,
+        );
+        assert_eq!(issues.len(), 1);
+
+        let issues = device.validate_texture(
+            TextureFormat::Rgba8Unorm,
+            TextureDimension::D2,
+            size,
+            TextureUsages::TEXTURE_BINDING,
+            4,
+        );
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_workgroup_size() {
+        let device = VirtualDevice::new(default_snapshot());
+        assert!(device.validate_workgroup_size([64, 1, 1]).is_empty());
+        assert!(!device.validate_workgroup_size([1024, 1, 1]).is_empty());
+        assert!(!device.validate_workgroup_size([16, 16, 2]).is_empty());
+    }
+
+    #[test]
+    fn test_validate_descriptor() {
+        let device = VirtualDevice::new(default_snapshot());
+        let buffer = ProjectDescriptor::Buffer {
+            label: "uniforms".to_string(),
+            size: device.limits().max_uniform_buffer_binding_size + 1,
+            usage: BufferUsages::UNIFORM,
+        };
+        assert_eq!(device.validate_descriptor(&buffer).len(), 1);
+
+        let workgroup = ProjectDescriptor::Workgroup {
+            entry_point: "main".to_string(),
+            size: [64, 1, 1],
+        };
+        assert!(device.validate_descriptor(&workgroup).is_empty());
+        assert_eq!(workgroup.to_string(), "Workgroup of main (64x1x1)");
+    }
+
+    #[test]
+    fn test_issue_display() {
+        let issue = CapabilityIssue::LimitExceeded {
+            limit: "max_bind_groups".to_string(),
+            requested: 8,
+            allowed: 4,
+        };
+        assert_eq!(
+            issue.to_string(),
+            "Limit exceeded: max_bind_groups requested 8, allowed 4"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Images pasted from the OS clipboard, and the resolution images were made at
+//!
+//! Screenshots copied to the clipboard arrive as bare RGBA pixels, so
+//! [`read_clipboard_image`] returns only those and the caller supplies the
+//! DPI, normally that of the display the screenshot was taken on. The
+//! Textures tab keeps loaded images encoded, so a pasted image is stored with
+//! [`encode_png`], which writes the DPI to the PNG `pHYs` chunk. [`image_dpi`]
+//! reads it back from there, or from the JFIF header of a JPEG, for pasted
+//! and loaded images alike.
+
+use std::fmt;
+use std::io::Cursor;
+
+use crate::asset_loader::DecodedImage;
+
+/// Resolution at which one image pixel is one logical point
+pub const BASE_DPI: f32 = 96.0;
+
+const METERS_PER_INCH: f32 = 0.0254;
+const CENTIMETERS_PER_INCH: f32 = 2.54;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Why no image could be taken from the clipboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardImageError {
+    /// The clipboard couldn't be opened or read
+    Unavailable(String),
+    /// The clipboard holds no image, e.g. only text
+    NoImage,
+    /// The pixel data doesn't match the image size
+    Malformed,
+}
+
+impl fmt::Display for ClipboardImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardImageError::Unavailable(e) => write!(f, "clipboard unavailable: {}", e),
+            ClipboardImageError::NoImage => write!(f, "the clipboard holds no image"),
+            ClipboardImageError::Malformed => write!(f, "the clipboard image is malformed"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardImageError {}
+
+/// Take the image on the OS clipboard
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_clipboard_image() -> Result<DecodedImage, ClipboardImageError> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| ClipboardImageError::Unavailable(e.to_string()))?;
+    let image = clipboard.get_image().map_err(|e| match e {
+        arboard::Error::ContentNotAvailable => ClipboardImageError::NoImage,
+        e => ClipboardImageError::Unavailable(e.to_string()),
+    })?;
+    image_from_rgba(image.width, image.height, image.bytes.into_owned())
+}
+
+/// Check that `rgba` holds exactly `width` by `height` pixels
+pub fn image_from_rgba(
+    width: usize,
+    height: usize,
+    rgba: Vec<u8>,
+) -> Result<DecodedImage, ClipboardImageError> {
+    let expected = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(4));
+    if width == 0 || height == 0 || expected != Some(rgba.len()) {
+        return Err(ClipboardImageError::Malformed);
+    }
+    Ok(DecodedImage {
+        width: u32::try_from(width).map_err(|_| ClipboardImageError::Malformed)?,
+        height: u32::try_from(height).map_err(|_| ClipboardImageError::Malformed)?,
+        rgba,
+    })
+}
+
+/// Encode `image` as PNG, recording `dpi` when given
+pub fn encode_png(image: &DecodedImage, dpi: Option<f32>) -> Result<Vec<u8>, png::EncodingError> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    if let Some(dpi) = dpi.filter(|dpi| *dpi > 0.0) {
+        let per_meter = (dpi / METERS_PER_INCH).round() as u32;
+        encoder.set_pixel_dims(Some(png::PixelDimensions {
+            xppu: per_meter,
+            yppu: per_meter,
+            unit: png::Unit::Meter,
+        }));
+    }
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&image.rgba)?;
+    writer.finish()?;
+    Ok(bytes)
+}
+
+/// Horizontal resolution recorded in a PNG or JPEG file, rounded to whole
+/// dots per inch
+///
+/// Files that give only an aspect ratio, or no resolution, have none.
+pub fn image_dpi(bytes: &[u8]) -> Option<f32> {
+    let dpi = if bytes.starts_with(&PNG_SIGNATURE) {
+        png_dpi(bytes)?
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        jpeg_dpi(bytes)?
+    } else {
+        return None;
+    };
+    (dpi > 0.0).then(|| dpi.round())
+}
+
+/// Resolution from the `pHYs` chunk, which precedes the image data
+fn png_dpi(bytes: &[u8]) -> Option<f32> {
+    let reader = png::Decoder::new(Cursor::new(bytes)).read_info().ok()?;
+    match reader.info().pixel_dims? {
+        png::PixelDimensions {
+            xppu,
+            unit: png::Unit::Meter,
+            ..
+        } => Some(xppu as f32 * METERS_PER_INCH),
+        _ => None,
+    }
+}
+
+/// Resolution from the JFIF APP0 segment, found before the first scan
+fn jpeg_dpi(bytes: &[u8]) -> Option<f32> {
+    let mut offset = 2;
+    loop {
+        let header = bytes.get(offset..offset + 4)?;
+        if header[0] != 0xFF {
+            return None;
+        }
+        let marker = header[1];
+        let length = u16::from_be_bytes([header[2], header[3]]) as usize;
+        // Start of scan: the headers are over
+        if marker == 0xDA {
+            return None;
+        }
+        let segment = bytes.get(offset + 4..offset + 2 + length)?;
+        if marker == 0xE0 && segment.starts_with(b"JFIF\0") && segment.len() >= 12 {
+            let density = u16::from_be_bytes([segment[8], segment[9]]) as f32;
+            return match segment[7] {
+                1 => Some(density),
+                2 => Some(density * CENTIMETERS_PER_INCH),
+                _ => None,
+            };
+        }
+        offset += 2 + length;
+    }
+}
+
+/// How many image pixels make one logical point at `dpi`
+pub fn scale_factor(dpi: f32) -> f32 {
+    dpi / BASE_DPI
+}
+
+/// Size in logical points of a `width` by `height` image made at `dpi`,
+/// e.g. half the pixels of a screenshot taken on a 2x display
+pub fn logical_size(width: u32, height: u32, dpi: f32) -> (u32, u32) {
+    let scale = scale_factor(dpi);
+    let logical = |pixels: u32| ((pixels as f32 / scale).round() as u32).max(1);
+    (logical(width), logical(height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(width: u32, height: u32) -> DecodedImage {
+        DecodedImage {
+            width,
+            height,
+            rgba: (0..width * height * 4).map(|i| i as u8).collect(),
+        }
+    }
+
+    #[test]
+    fn test_png_roundtrip_keeps_pixels_and_dpi() {
+        let original = image(3, 2);
+        let bytes = encode_png(&original, Some(192.0)).unwrap();
+        assert_eq!(image_dpi(&bytes), Some(192.0));
+        let decoded = crate::asset_loader::decode_image(&bytes).unwrap();
+        assert_eq!(decoded, original);
+
+        let without_dpi = encode_png(&original, None).unwrap();
+        assert_eq!(image_dpi(&without_dpi), None);
+    }
+
+    #[test]
+    fn test_jpeg_jfif_density() {
+        // SOI, then an APP0 JFIF segment with the given units and density
+        let jfif = |units: u8, density: u16| {
+            let mut bytes = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+            bytes.extend_from_slice(b"JFIF\0");
+            bytes.extend_from_slice(&[1, 2, units]);
+            bytes.extend_from_slice(&density.to_be_bytes());
+            bytes.extend_from_slice(&density.to_be_bytes());
+            bytes.extend_from_slice(&[0, 0]);
+            bytes.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02]);
+            bytes
+        };
+        assert_eq!(image_dpi(&jfif(1, 144)), Some(144.0));
+        assert_eq!(image_dpi(&jfif(2, 118)), Some(300.0));
+        // Aspect ratio only
+        assert_eq!(image_dpi(&jfif(0, 1)), None);
+        assert_eq!(image_dpi(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn test_logical_size_and_clipboard_data() {
+        assert_eq!(logical_size(2560, 1440, 192.0), (1280, 720));
+        assert_eq!(logical_size(100, 1, BASE_DPI), (100, 1));
+        assert_eq!(logical_size(1, 1, 288.0), (1, 1));
+
+        assert_eq!(image_from_rgba(2, 1, vec![0; 8]).unwrap().width, 2);
+        assert_eq!(
+            image_from_rgba(2, 2, vec![0; 8]),
+            Err(ClipboardImageError::Malformed)
+        );
+        assert_eq!(
+            image_from_rgba(0, 0, Vec::new()),
+            Err(ClipboardImageError::Malformed)
+        );
+    }
+}
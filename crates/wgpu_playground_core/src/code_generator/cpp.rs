@@ -0,0 +1,1548 @@
+//! C++ backend of the code generator
+//!
+//! Writes a CMake project whose `main.cpp` recreates the playground setup
+//! with the webgpu.h API and renders it into a GLFW window. CMake builds
+//! Dawn from source, or uses an existing checkout given as `DAWN_SOURCE_DIR`.
+
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use super::{
+    parse_or, variant, CodeGenConfig, CodeGenerator, DEFAULT_BUFFER_SIZE, DEFAULT_TEXTURE_DEPTH,
+    DEFAULT_TEXTURE_HEIGHT, DEFAULT_TEXTURE_WIDTH,
+};
+use crate::state::{
+    BindGroupEntryState, BufferPanelState, ComputePipelinePanelState, PlaygroundState,
+    RenderPipelinePanelState, SamplerPanelState, ShaderEditorState, TexturePanelState,
+};
+
+/// Header next to main.cpp holding the RGBA8 pixels of the texture's image
+const TEXTURE_DATA_HEADER: &str = "texture_data.h";
+
+/// Includes and helpers at the top of every main.cpp
+const PRELUDE: &str = "#include <webgpu/webgpu.h>\n\
+#include <webgpu/webgpu_glfw.h>\n\
+\n\
+#include <GLFW/glfw3.h>\n\
+\n\
+#include <cstdint>\n\
+#include <cstdio>\n\
+#include <cstring>\n\
+#include <vector>\n";
+
+/// Helpers of every main.cpp requesting the adapter and device
+const HELPERS: &str = "/// String view of a null-terminated string\n\
+static WGPUStringView str(const char* value) {\n    \
+    return WGPUStringView{value, WGPU_STRLEN};\n\
+}\n\
+\n\
+/// Entry point used when the pipeline doesn't name one: the shader's only\n\
+/// entry point of the stage\n\
+static const WGPUStringView DEFAULT_ENTRY_POINT = WGPUStringView{nullptr, WGPU_STRLEN};\n\
+\n\
+/// Print a message passed to a callback\n\
+static void print_message(const char* what, WGPUStringView message) {\n    \
+    if (!message.data) {\n        \
+        std::fprintf(stderr, \"%s\\n\", what);\n        \
+        return;\n    \
+    }\n    \
+    size_t length = message.length == WGPU_STRLEN ? std::strlen(message.data) : message.length;\n    \
+    std::fprintf(stderr, \"%s: %.*s\\n\", what, static_cast<int>(length), message.data);\n\
+}\n\
+\n\
+/// Request an adapter that can present to the surface and wait for it\n\
+static WGPUAdapter request_adapter(WGPUInstance instance, WGPUSurface surface) {\n    \
+    WGPURequestAdapterOptions options = {};\n    \
+    options.compatibleSurface = surface;\n\
+\n    \
+    struct Request {\n        \
+        bool done = false;\n        \
+        WGPUAdapter adapter = nullptr;\n    \
+    } request;\n    \
+    WGPURequestAdapterCallbackInfo callback_info = {};\n    \
+    callback_info.mode = WGPUCallbackMode_AllowProcessEvents;\n    \
+    callback_info.callback = [](WGPURequestAdapterStatus status, WGPUAdapter adapter,\n                                \
+                                WGPUStringView message, void* userdata, void*) {\n        \
+        auto* request = static_cast<Request*>(userdata);\n        \
+        if (status == WGPURequestAdapterStatus_Success) {\n            \
+            request->adapter = adapter;\n        \
+        } else {\n            \
+            print_message(\"Failed to get an adapter\", message);\n        \
+        }\n        \
+        request->done = true;\n    \
+    };\n    \
+    callback_info.userdata1 = &request;\n\
+\n    \
+    wgpuInstanceRequestAdapter(instance, &options, callback_info);\n    \
+    while (!request.done) {\n        \
+        wgpuInstanceProcessEvents(instance);\n    \
+    }\n    \
+    return request.adapter;\n\
+}\n\
+\n\
+/// Request a device with the given features and wait for it\n\
+static WGPUDevice request_device(WGPUInstance instance, WGPUAdapter adapter,\n                                 \
+                                 const std::vector<WGPUFeatureName>& features) {\n    \
+    WGPUDeviceDescriptor descriptor = {};\n    \
+    descriptor.label = str(\"Device\");\n    \
+    descriptor.requiredFeatureCount = features.size();\n    \
+    descriptor.requiredFeatures = features.data();\n    \
+    descriptor.uncapturedErrorCallbackInfo.callback = [](WGPUDevice const*, WGPUErrorType,\n                                                         \
+                                                         WGPUStringView message, void*, void*) {\n        \
+        print_message(\"WebGPU error\", message);\n    \
+    };\n\
+\n    \
+    struct Request {\n        \
+        bool done = false;\n        \
+        WGPUDevice device = nullptr;\n    \
+    } request;\n    \
+    WGPURequestDeviceCallbackInfo callback_info = {};\n    \
+    callback_info.mode = WGPUCallbackMode_AllowProcessEvents;\n    \
+    callback_info.callback = [](WGPURequestDeviceStatus status, WGPUDevice device,\n                                \
+                                WGPUStringView message, void* userdata, void*) {\n        \
+        auto* request = static_cast<Request*>(userdata);\n        \
+        if (status == WGPURequestDeviceStatus_Success) {\n            \
+            request->device = device;\n        \
+        } else {\n            \
+            print_message(\"Failed to get a device\", message);\n        \
+        }\n        \
+        request->done = true;\n    \
+    };\n    \
+    callback_info.userdata1 = &request;\n\
+\n    \
+    wgpuAdapterRequestDevice(adapter, &descriptor, callback_info);\n    \
+    while (!request.done) {\n        \
+        wgpuInstanceProcessEvents(instance);\n    \
+    }\n    \
+    return request.device;\n\
+}\n\
+\n";
+
+/// Helper of the generated projects creating the depth and multisampled
+/// color targets at the surface size
+const ATTACHMENT_HELPER: &str = "/// Render target matching the surface size\n\
+static WGPUTextureView create_attachment(const State& state, WGPUTextureFormat format,\n                                         \
+                                         uint32_t sample_count, const char* label) {\n    \
+    WGPUTextureDescriptor descriptor = {};\n    \
+    descriptor.label = str(label);\n    \
+    descriptor.size = {state.config.width, state.config.height, 1};\n    \
+    descriptor.mipLevelCount = 1;\n    \
+    descriptor.sampleCount = sample_count;\n    \
+    descriptor.dimension = WGPUTextureDimension_2D;\n    \
+    descriptor.format = format;\n    \
+    descriptor.usage = WGPUTextureUsage_RenderAttachment;\n    \
+    WGPUTexture texture = wgpuDeviceCreateTexture(state.device, &descriptor);\n    \
+    // The view keeps the texture alive\n    \
+    WGPUTextureView view = wgpuTextureCreateView(texture, nullptr);\n    \
+    wgpuTextureRelease(texture);\n    \
+    return view;\n\
+}\n\
+\n";
+
+/// webgpu.h enumerant of type `ty` for a saved `Debug` name, for the enums
+/// whose variants webgpu.h names like wgpu
+fn enumerant(ty: &str, name: &str, default: &str) -> String {
+    format!("WGPU{}_{}", ty, variant(name, default))
+}
+
+/// webgpu.h name of a texture format saved as its `wgpu::TextureFormat` name
+///
+/// webgpu.h writes the channels in upper case, e.g. `Rgb10a2Unorm` is
+/// `RGB10A2Unorm` and `Bc6hRgbUfloat` is `BC6HRGBUfloat`.
+fn texture_format(name: &str, default: &str) -> String {
+    let name = variant(name, default);
+    if !(name.starts_with('R') || name.starts_with("Bgra") || name.starts_with("Bc")) {
+        return format!("WGPUTextureFormat_{}", name);
+    }
+    // Channels and bit counts end at the first word after a digit
+    let digit = name.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+    let mut end = name[digit..]
+        .find(|c: char| c.is_ascii_uppercase())
+        .map_or(name.len(), |i| digit + i);
+    // Compressed formats name their channels after the block format
+    if name.starts_with("Bc") && end < name.len() {
+        end += name[end..]
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| c.is_ascii_uppercase())
+            .map_or(name.len() - end, |(i, _)| i);
+    }
+    format!(
+        "WGPUTextureFormat_{}{}",
+        name[..end].to_ascii_uppercase(),
+        &name[end..]
+    )
+}
+
+/// C++ string literal of `value`
+fn string_literal(value: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            // Octal escapes end after three digits, unlike hex escapes
+            c if c.is_control() => {
+                for byte in c.to_string().bytes() {
+                    let _ = write!(literal, "\\{:03o}", byte);
+                }
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// `a | b | c` of the enabled flags, or `default` if none is
+fn flags(flags: &[(bool, &str)], default: &str) -> String {
+    let enabled: Vec<&str> = flags
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, flag)| *flag)
+        .collect();
+    if enabled.is_empty() {
+        default.to_string()
+    } else {
+        enabled.join(" | ")
+    }
+}
+
+/// Entry point of a pipeline, or the shader's only one of the stage
+fn entry_point(name: &str) -> String {
+    if name.is_empty() {
+        "DEFAULT_ENTRY_POINT".to_string()
+    } else {
+        format!("str({})", string_literal(name))
+    }
+}
+
+/// Writes the C++ project of a render setup
+pub(super) struct CppGenerator<'a> {
+    config: &'a CodeGenConfig,
+    /// The playground state, or a triangle drawn with the configured shader
+    state: Cow<'a, PlaygroundState>,
+    /// The texture's image, decoded to RGBA8
+    image: Option<image::RgbaImage>,
+}
+
+impl<'a> CppGenerator<'a> {
+    pub(super) fn new(config: &'a CodeGenConfig) -> Self {
+        let state = CodeGenerator::state_or_triangle(config);
+        let image = state
+            .texture_panel
+            .as_ref()
+            .and_then(CodeGenerator::texture_image);
+        Self {
+            config,
+            state,
+            image,
+        }
+    }
+
+    /// Write CMakeLists.txt, main.cpp, the texture's pixels and README.md
+    pub(super) fn generate(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        std::fs::create_dir_all(output_dir)?;
+        std::fs::write(output_dir.join("CMakeLists.txt"), self.cmake_lists())?;
+        std::fs::write(output_dir.join("main.cpp"), self.main_cpp())?;
+        if let Some(image) = &self.image {
+            std::fs::write(
+                output_dir.join(TEXTURE_DATA_HEADER),
+                Self::texture_data_header(image),
+            )?;
+        }
+        std::fs::write(output_dir.join("README.md"), self.readme())
+    }
+
+    /// CMakeLists.txt building main.cpp against Dawn and GLFW
+    fn cmake_lists(&self) -> String {
+        format!(
+            "cmake_minimum_required(VERSION 3.22)\n\
+            project({name} LANGUAGES C CXX)\n\
+            \n\
+            set(CMAKE_CXX_STANDARD 20)\n\
+            set(CMAKE_CXX_STANDARD_REQUIRED ON)\n\
+            \n\
+            # Dawn is built from source, along with GLFW. Point DAWN_SOURCE_DIR at an\n\
+            # existing checkout to skip the download.\n\
+            set(DAWN_SOURCE_DIR \"\" CACHE PATH \"Dawn checkout to build against\")\n\
+            set(DAWN_GIT_TAG \"main\" CACHE STRING \"Dawn revision downloaded without DAWN_SOURCE_DIR\")\n\
+            \n\
+            set(DAWN_FETCH_DEPENDENCIES ON CACHE BOOL \"\" FORCE)\n\
+            set(DAWN_USE_GLFW ON CACHE BOOL \"\" FORCE)\n\
+            set(DAWN_BUILD_SAMPLES OFF CACHE BOOL \"\" FORCE)\n\
+            set(DAWN_BUILD_TESTS OFF CACHE BOOL \"\" FORCE)\n\
+            set(TINT_BUILD_TESTS OFF CACHE BOOL \"\" FORCE)\n\
+            set(TINT_BUILD_CMD_TOOLS OFF CACHE BOOL \"\" FORCE)\n\
+            \n\
+            if(DAWN_SOURCE_DIR)\n    \
+                add_subdirectory(${{DAWN_SOURCE_DIR}} ${{CMAKE_BINARY_DIR}}/dawn EXCLUDE_FROM_ALL)\n\
+            else()\n    \
+                include(FetchContent)\n    \
+                FetchContent_Declare(\n        \
+                    dawn\n        \
+                    GIT_REPOSITORY https://dawn.googlesource.com/dawn\n        \
+                    GIT_TAG ${{DAWN_GIT_TAG}}\n        \
+                    GIT_SHALLOW TRUE\n    \
+                )\n    \
+                FetchContent_MakeAvailable(dawn)\n\
+            endif()\n\
+            \n\
+            add_executable({name} main.cpp)\n\
+            target_link_libraries({name} PRIVATE dawn::webgpu_dawn webgpu_glfw glfw)\n",
+            name = self.config.project_name
+        )
+    }
+
+    /// texture_data.h with the image's pixels as a byte array
+    fn texture_data_header(image: &image::RgbaImage) -> String {
+        let mut code = format!(
+            "// RGBA8 pixels of the image loaded in the playground, {}x{}\n\
+            #pragma once\n\
+            \n\
+            #include <cstdint>\n\
+            \n\
+            static const uint8_t TEXTURE_DATA[] = {{\n",
+            image.width(),
+            image.height()
+        );
+        for row in image.as_raw().chunks(16) {
+            code.push_str("   ");
+            for byte in row {
+                let _ = write!(code, " 0x{:02x},", byte);
+            }
+            code.push('\n');
+        }
+        code.push_str("};\n");
+        code
+    }
+
+    /// main.cpp recreating the playground setup
+    fn main_cpp(&self) -> String {
+        let state = self.state.as_ref();
+        let mut code = String::from(PRELUDE);
+        if self.image.is_some() {
+            let _ = writeln!(code, "\n#include \"{}\"", TEXTURE_DATA_HEADER);
+        }
+        code.push('\n');
+
+        // WGSL has no string literals, so the raw string can't end early
+        if let Some(shader_editor) = &state.shader_editor {
+            let _ = write!(
+                code,
+                "static const char* SHADER_SOURCE = R\"wgsl({})wgsl\";\n\n",
+                shader_editor.source_code
+            );
+        }
+
+        code.push_str(HELPERS);
+        code.push_str(&self.state_struct());
+        if CodeGenerator::needs_attachments(state) {
+            code.push_str(ATTACHMENT_HELPER);
+        }
+        code.push_str(&self.init_function());
+        code.push_str(&self.resize_function());
+        code.push_str(&self.render_function());
+        code.push_str(&self.release_function());
+        code.push_str(&self.main_function());
+        code
+    }
+
+    /// `WGPUFeatureName`s the configuration needs
+    fn required_features(&self) -> Vec<&'static str> {
+        let state = self.state.as_ref();
+        let mut features = Vec::new();
+        if let Some(pipeline) = CodeGenerator::render_pipeline_state(state) {
+            if pipeline.enable_depth_stencil && pipeline.depth_format == "Depth32FloatStencil8" {
+                features.push("WGPUFeatureName_Depth32FloatStencil8");
+            }
+        }
+        if let Some(texture) = &state.texture_panel {
+            if texture.format.starts_with("Bc") {
+                features.push("WGPUFeatureName_TextureCompressionBC");
+            }
+        }
+        features
+    }
+
+    /// Fields of the `State` struct: the objects every frame uses and those
+    /// released on exit
+    fn state_struct(&self) -> String {
+        let mut code = String::from(
+            "/// Everything the playground setup created, kept for the whole run\n\
+            struct State {\n    \
+                WGPUInstance instance = nullptr;\n    \
+                WGPUSurface surface = nullptr;\n    \
+                WGPUAdapter adapter = nullptr;\n    \
+                WGPUDevice device = nullptr;\n    \
+                WGPUQueue queue = nullptr;\n    \
+                WGPUSurfaceConfiguration config = {};\n",
+        );
+        for (field, ty) in self.resources() {
+            let _ = writeln!(code, "    {} {} = nullptr;", ty, field);
+        }
+        code.push_str("};\n\n");
+        code
+    }
+
+    /// `State` fields holding resources and their types, in creation order
+    fn resources(&self) -> Vec<(&'static str, &'static str)> {
+        let state = self.state.as_ref();
+        let mut resources = Vec::new();
+        if state.buffer_panel.is_some() {
+            resources.push(("buffer", "WGPUBuffer"));
+        }
+        if state.texture_panel.is_some() {
+            resources.push(("texture", "WGPUTexture"));
+            resources.push(("texture_view", "WGPUTextureView"));
+        }
+        if state.sampler_panel.is_some() {
+            resources.push(("sampler", "WGPUSampler"));
+        }
+        if state.shader_editor.is_some() {
+            resources.push(("shader_module", "WGPUShaderModule"));
+        }
+        if !CodeGenerator::bind_group_entries(state).is_empty() {
+            resources.push(("bind_group", "WGPUBindGroup"));
+        }
+        if let Some(pipeline) = CodeGenerator::render_pipeline_state(state) {
+            resources.push(("render_pipeline", "WGPURenderPipeline"));
+            if pipeline.enable_depth_stencil {
+                resources.push(("depth_view", "WGPUTextureView"));
+            }
+            if pipeline.sample_count > 1 {
+                resources.push(("msaa_view", "WGPUTextureView"));
+            }
+        }
+        if CodeGenerator::compute_pipeline_state(state).is_some() {
+            resources.push(("compute_pipeline", "WGPUComputePipeline"));
+        }
+        resources
+    }
+
+    /// `init`, which sets up the device and surface and creates the
+    /// configured resources
+    fn init_function(&self) -> String {
+        let state = self.state.as_ref();
+        let mut code = format!(
+            "/// Create the device, configure the surface and create everything set up in\n\
+            /// the playground\n\
+            static bool init(State& state, GLFWwindow* window) {{\n    \
+                state.instance = wgpuCreateInstance(nullptr);\n    \
+                state.surface =\n        \
+                    wgpu::glfw::CreateSurfaceForWindow(wgpu::Instance(state.instance), window)\n            \
+                        .MoveToCHandle();\n    \
+                state.adapter = request_adapter(state.instance, state.surface);\n    \
+                if (!state.adapter) {{\n        \
+                    return false;\n    \
+                }}\n    \
+                state.device = request_device(state.instance, state.adapter, {{{}}});\n    \
+                if (!state.device) {{\n        \
+                    return false;\n    \
+                }}\n    \
+                state.queue = wgpuDeviceGetQueue(state.device);\n\
+                \n    \
+                WGPUSurfaceCapabilities capabilities = {{}};\n    \
+                wgpuSurfaceGetCapabilities(state.surface, state.adapter, &capabilities);\n    \
+                int width = 0;\n    \
+                int height = 0;\n    \
+                glfwGetFramebufferSize(window, &width, &height);\n    \
+                state.config.device = state.device;\n    \
+                state.config.format = capabilities.formats[0];\n    \
+                state.config.usage = WGPUTextureUsage_RenderAttachment;\n    \
+                state.config.width = width > 0 ? static_cast<uint32_t>(width) : 1;\n    \
+                state.config.height = height > 0 ? static_cast<uint32_t>(height) : 1;\n    \
+                state.config.alphaMode = capabilities.alphaModes[0];\n    \
+                state.config.presentMode = WGPUPresentMode_Fifo;\n    \
+                wgpuSurfaceCapabilitiesFreeMembers(capabilities);\n    \
+                wgpuSurfaceConfigure(state.surface, &state.config);\n\n",
+            self.required_features().join(", ")
+        );
+
+        if let Some(buffer_state) = &state.buffer_panel {
+            code.push_str(&Self::buffer_creation(buffer_state));
+        }
+        if let Some(texture_state) = &state.texture_panel {
+            code.push_str(&self.texture_creation(texture_state));
+        }
+        if let Some(sampler_state) = &state.sampler_panel {
+            code.push_str(&Self::sampler_creation(sampler_state));
+        }
+        if let Some(shader_state) = &state.shader_editor {
+            code.push_str(&Self::shader_module_creation(shader_state));
+        }
+
+        let has_bind_group = !CodeGenerator::bind_group_entries(state).is_empty();
+        if has_bind_group {
+            code.push_str(&self.bind_group_creation());
+        }
+
+        // Both pipelines share a layout with the bind group at index 0
+        let render_pipeline = CodeGenerator::render_pipeline_state(state);
+        let compute_pipeline = CodeGenerator::compute_pipeline_state(state);
+        if render_pipeline.is_some() || compute_pipeline.is_some() {
+            code.push_str(
+                "    WGPUPipelineLayoutDescriptor pipeline_layout_descriptor = {};\n    \
+                pipeline_layout_descriptor.label = str(\"Pipeline Layout\");\n",
+            );
+            if has_bind_group {
+                code.push_str(
+                    "    pipeline_layout_descriptor.bindGroupLayoutCount = 1;\n    \
+                    pipeline_layout_descriptor.bindGroupLayouts = &bind_group_layout;\n",
+                );
+            }
+            code.push_str(
+                "    WGPUPipelineLayout pipeline_layout =\n        \
+                    wgpuDeviceCreatePipelineLayout(state.device, &pipeline_layout_descriptor);\n\n",
+            );
+        }
+        if let Some(pipeline_state) = render_pipeline {
+            code.push_str(&Self::render_pipeline_creation(pipeline_state));
+        }
+        if let Some(compute_state) = compute_pipeline {
+            code.push_str(&Self::compute_pipeline_creation(compute_state));
+        }
+
+        // The pipelines and the bind group keep their layouts alive
+        if render_pipeline.is_some() || compute_pipeline.is_some() {
+            code.push_str("    wgpuPipelineLayoutRelease(pipeline_layout);\n");
+        }
+        if has_bind_group {
+            code.push_str("    wgpuBindGroupLayoutRelease(bind_group_layout);\n");
+        }
+        code.push_str("    return true;\n}\n\n");
+        code
+    }
+
+    /// Buffer creation code
+    fn buffer_creation(buffer_state: &BufferPanelState) -> String {
+        let mut size = parse_or(&buffer_state.size, DEFAULT_BUFFER_SIZE, "buffer size");
+        // Buffers mapped at creation need a size aligned to 4 bytes
+        if buffer_state.mapped_at_creation {
+            size = size.next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+        }
+        let usage = flags(
+            &[
+                (buffer_state.usage_vertex, "WGPUBufferUsage_Vertex"),
+                (buffer_state.usage_index, "WGPUBufferUsage_Index"),
+                (buffer_state.usage_uniform, "WGPUBufferUsage_Uniform"),
+                (buffer_state.usage_storage, "WGPUBufferUsage_Storage"),
+                (buffer_state.usage_indirect, "WGPUBufferUsage_Indirect"),
+                (buffer_state.usage_copy_src, "WGPUBufferUsage_CopySrc"),
+                (buffer_state.usage_copy_dst, "WGPUBufferUsage_CopyDst"),
+                (buffer_state.usage_map_read, "WGPUBufferUsage_MapRead"),
+                (buffer_state.usage_map_write, "WGPUBufferUsage_MapWrite"),
+                (
+                    buffer_state.usage_query_resolve,
+                    "WGPUBufferUsage_QueryResolve",
+                ),
+            ],
+            "WGPUBufferUsage_Vertex",
+        );
+
+        let mut code = format!(
+            "    WGPUBufferDescriptor buffer_descriptor = {{}};\n    \
+            buffer_descriptor.label = str({});\n    \
+            buffer_descriptor.size = {};\n    \
+            buffer_descriptor.usage = {};\n    \
+            buffer_descriptor.mappedAtCreation = {};\n    \
+            state.buffer = wgpuDeviceCreateBuffer(state.device, &buffer_descriptor);\n",
+            string_literal(&buffer_state.label),
+            size,
+            usage,
+            buffer_state.mapped_at_creation
+        );
+        if buffer_state.mapped_at_creation {
+            code.push_str("    wgpuBufferUnmap(state.buffer);\n");
+        }
+        code.push('\n');
+        code
+    }
+
+    /// Texture creation code, uploading the loaded image if any
+    fn texture_creation(&self, texture_state: &TexturePanelState) -> String {
+        let (width, height, depth) = match &self.image {
+            Some(image) => (image.width(), image.height(), 1),
+            None => (
+                parse_or(&texture_state.width, DEFAULT_TEXTURE_WIDTH, "texture width"),
+                parse_or(
+                    &texture_state.height,
+                    DEFAULT_TEXTURE_HEIGHT,
+                    "texture height",
+                ),
+                parse_or(&texture_state.depth, DEFAULT_TEXTURE_DEPTH, "texture depth"),
+            ),
+        };
+        let mip_levels = parse_or(&texture_state.mip_levels, 1, "texture mip level count");
+        let sample_count = parse_or(&texture_state.sample_count, 1, "texture sample count");
+        let dimension = match variant(&texture_state.dimension, "D2") {
+            "D1" => "WGPUTextureDimension_1D",
+            "D3" => "WGPUTextureDimension_3D",
+            _ => "WGPUTextureDimension_2D",
+        };
+        let usage = flags(
+            &[
+                (texture_state.usage_copy_src, "WGPUTextureUsage_CopySrc"),
+                // The image is written to the texture
+                (
+                    texture_state.usage_copy_dst || self.image.is_some(),
+                    "WGPUTextureUsage_CopyDst",
+                ),
+                (
+                    texture_state.usage_texture_binding,
+                    "WGPUTextureUsage_TextureBinding",
+                ),
+                (
+                    texture_state.usage_storage_binding,
+                    "WGPUTextureUsage_StorageBinding",
+                ),
+                (
+                    texture_state.usage_render_attachment,
+                    "WGPUTextureUsage_RenderAttachment",
+                ),
+            ],
+            "WGPUTextureUsage_TextureBinding",
+        );
+
+        let mut code = format!(
+            "    WGPUTextureDescriptor texture_descriptor = {{}};\n    \
+            texture_descriptor.label = str({});\n    \
+            texture_descriptor.size = {{{}, {}, {}}};\n    \
+            texture_descriptor.mipLevelCount = {};\n    \
+            texture_descriptor.sampleCount = {};\n    \
+            texture_descriptor.dimension = {};\n    \
+            texture_descriptor.format = {};\n    \
+            texture_descriptor.usage = {};\n    \
+            state.texture = wgpuDeviceCreateTexture(state.device, &texture_descriptor);\n",
+            string_literal(&texture_state.label),
+            width,
+            height,
+            depth,
+            mip_levels,
+            sample_count,
+            dimension,
+            texture_format(&texture_state.format, "Rgba8UnormSrgb"),
+            usage
+        );
+        if self.image.is_some() {
+            let _ = write!(
+                code,
+                "    // The image loaded in the playground, as RGBA8 pixels\n    \
+                WGPUTexelCopyTextureInfo texture_destination = {{}};\n    \
+                texture_destination.texture = state.texture;\n    \
+                texture_destination.aspect = WGPUTextureAspect_All;\n    \
+                WGPUTexelCopyBufferLayout texture_data_layout = {{}};\n    \
+                texture_data_layout.bytesPerRow = {};\n    \
+                texture_data_layout.rowsPerImage = {};\n    \
+                WGPUExtent3D texture_data_size = {{{}, {}, 1}};\n    \
+                wgpuQueueWriteTexture(state.queue, &texture_destination, TEXTURE_DATA,\n                          \
+                                      sizeof(TEXTURE_DATA), &texture_data_layout, &texture_data_size);\n",
+                width * 4,
+                height,
+                width,
+                height
+            );
+        }
+        code.push_str(
+            "    state.texture_view = wgpuTextureCreateView(state.texture, nullptr);\n\n",
+        );
+        code
+    }
+
+    /// Sampler creation code
+    fn sampler_creation(sampler_state: &SamplerPanelState) -> String {
+        let lod_min_clamp = parse_or(&sampler_state.lod_min_clamp, 0.0f32, "LOD min clamp");
+        let lod_max_clamp = parse_or(&sampler_state.lod_max_clamp, 32.0f32, "LOD max clamp");
+        let anisotropy = parse_or(&sampler_state.max_anisotropy, 1u16, "anisotropy").clamp(1, 16);
+        let address_mode = |name: &str| {
+            if name == "ClampToBorder" {
+                log::warn!("webgpu.h has no ClampToBorder address mode. Using ClampToEdge.");
+                return enumerant("AddressMode", "ClampToEdge", "ClampToEdge");
+            }
+            enumerant("AddressMode", name, "ClampToEdge")
+        };
+        let compare = match &sampler_state.compare {
+            Some(compare) => enumerant("CompareFunction", compare, "LessEqual"),
+            None => "WGPUCompareFunction_Undefined".to_string(),
+        };
+
+        format!(
+            "    WGPUSamplerDescriptor sampler_descriptor = {{}};\n    \
+            sampler_descriptor.label = str({});\n    \
+            sampler_descriptor.addressModeU = {};\n    \
+            sampler_descriptor.addressModeV = {};\n    \
+            sampler_descriptor.addressModeW = {};\n    \
+            sampler_descriptor.magFilter = {};\n    \
+            sampler_descriptor.minFilter = {};\n    \
+            sampler_descriptor.mipmapFilter = {};\n    \
+            sampler_descriptor.lodMinClamp = {:?}f;\n    \
+            sampler_descriptor.lodMaxClamp = {:?}f;\n    \
+            sampler_descriptor.compare = {};\n    \
+            sampler_descriptor.maxAnisotropy = {};\n    \
+            state.sampler = wgpuDeviceCreateSampler(state.device, &sampler_descriptor);\n\n",
+            string_literal(&sampler_state.label),
+            address_mode(&sampler_state.address_mode_u),
+            address_mode(&sampler_state.address_mode_v),
+            address_mode(&sampler_state.address_mode_w),
+            enumerant("FilterMode", &sampler_state.mag_filter, "Linear"),
+            enumerant("FilterMode", &sampler_state.min_filter, "Linear"),
+            enumerant("MipmapFilterMode", &sampler_state.mipmap_filter, "Linear"),
+            lod_min_clamp,
+            lod_max_clamp,
+            compare,
+            anisotropy
+        )
+    }
+
+    /// Shader module creation code
+    fn shader_module_creation(shader_state: &ShaderEditorState) -> String {
+        format!(
+            "    WGPUShaderSourceWGSL wgsl_source = {{}};\n    \
+            wgsl_source.chain.sType = WGPUSType_ShaderSourceWGSL;\n    \
+            wgsl_source.code = str(SHADER_SOURCE);\n    \
+            WGPUShaderModuleDescriptor shader_descriptor = {{}};\n    \
+            shader_descriptor.nextInChain = &wgsl_source.chain;\n    \
+            shader_descriptor.label = str({});\n    \
+            state.shader_module = wgpuDeviceCreateShaderModule(state.device, &shader_descriptor);\n\n",
+            string_literal(&shader_state.label)
+        )
+    }
+
+    /// The bind group layout and the bind group
+    ///
+    /// As in the Rust projects, entries no configured resource fits get a
+    /// placeholder, released once the bind group holds it.
+    fn bind_group_creation(&self) -> String {
+        let state = self.state.as_ref();
+        let entries = CodeGenerator::bind_group_entries(state);
+        let label = state
+            .bind_group_panel
+            .as_ref()
+            .filter(|panel| !panel.entries.is_empty())
+            .map(|panel| panel.label.as_str())
+            .or_else(|| {
+                state
+                    .bind_group_layout_panel
+                    .as_ref()
+                    .map(|p| p.label.as_str())
+            })
+            .filter(|label| !label.is_empty())
+            .unwrap_or("Bind Group");
+
+        let mut layout_entries = String::new();
+        let mut placeholders = String::new();
+        let mut releases = String::new();
+        let mut group_entries = String::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let visibility = flags(
+                &[
+                    (entry.visibility_vertex, "WGPUShaderStage_Vertex"),
+                    (entry.visibility_fragment, "WGPUShaderStage_Fragment"),
+                    (entry.visibility_compute, "WGPUShaderStage_Compute"),
+                ],
+                "WGPUShaderStage_None",
+            );
+            let _ = write!(
+                layout_entries,
+                "    layout_entries[{index}].binding = {};\n    \
+                layout_entries[{index}].visibility = {};\n",
+                entry.binding, visibility
+            );
+            let _ = writeln!(
+                group_entries,
+                "    group_entries[{index}].binding = {};",
+                entry.binding
+            );
+            self.bind_group_entry(
+                entry,
+                index,
+                &mut layout_entries,
+                &mut group_entries,
+                &mut placeholders,
+                &mut releases,
+            );
+        }
+
+        format!(
+            "    WGPUBindGroupLayoutEntry layout_entries[{count}] = {{}};\n\
+            {layout_entries}    \
+            WGPUBindGroupLayoutDescriptor bind_group_layout_descriptor = {{}};\n    \
+            bind_group_layout_descriptor.label = str({layout_label});\n    \
+            bind_group_layout_descriptor.entryCount = {count};\n    \
+            bind_group_layout_descriptor.entries = layout_entries;\n    \
+            WGPUBindGroupLayout bind_group_layout =\n        \
+                wgpuDeviceCreateBindGroupLayout(state.device, &bind_group_layout_descriptor);\n\
+            \n\
+            {placeholders}    \
+            WGPUBindGroupEntry group_entries[{count}] = {{}};\n\
+            {group_entries}    \
+            WGPUBindGroupDescriptor bind_group_descriptor = {{}};\n    \
+            bind_group_descriptor.label = str({label});\n    \
+            bind_group_descriptor.layout = bind_group_layout;\n    \
+            bind_group_descriptor.entryCount = {count};\n    \
+            bind_group_descriptor.entries = group_entries;\n    \
+            state.bind_group = wgpuDeviceCreateBindGroup(state.device, &bind_group_descriptor);\n\
+            {releases}\n",
+            count = entries.len(),
+            layout_label = string_literal(&format!("{} Layout", label)),
+            label = string_literal(label),
+        )
+    }
+
+    /// Binding type of a layout entry and resource of a bind group entry,
+    /// adding a placeholder resource when no configured resource fits
+    fn bind_group_entry(
+        &self,
+        entry: &BindGroupEntryState,
+        index: usize,
+        layout_entries: &mut String,
+        group_entries: &mut String,
+        placeholders: &mut String,
+        releases: &mut String,
+    ) {
+        let state = self.state.as_ref();
+        let binding = entry.binding;
+        let fits = CodeGenerator::configured_resource_fits(state, entry);
+        let mut placeholder_buffer = |usage: &str| {
+            let _ = write!(
+                placeholders,
+                "    // No configured buffer fits binding {binding}\n    \
+                WGPUBufferDescriptor binding_{binding}_buffer_descriptor = {{}};\n    \
+                binding_{binding}_buffer_descriptor.label = str(\"Binding {binding} Buffer\");\n    \
+                binding_{binding}_buffer_descriptor.size = {DEFAULT_BUFFER_SIZE};\n    \
+                binding_{binding}_buffer_descriptor.usage = {usage} | WGPUBufferUsage_CopyDst;\n    \
+                WGPUBuffer binding_{binding}_buffer =\n        \
+                    wgpuDeviceCreateBuffer(state.device, &binding_{binding}_buffer_descriptor);\n\n"
+            );
+            let _ = writeln!(releases, "    wgpuBufferRelease(binding_{binding}_buffer);");
+            format!("binding_{binding}_buffer")
+        };
+
+        match entry.binding_type.as_str() {
+            "UniformBuffer" | "StorageBuffer" => {
+                let (ty, usage) = match entry.binding_type.as_str() {
+                    "UniformBuffer" => ("WGPUBufferBindingType_Uniform", "WGPUBufferUsage_Uniform"),
+                    _ if entry.read_only => (
+                        "WGPUBufferBindingType_ReadOnlyStorage",
+                        "WGPUBufferUsage_Storage",
+                    ),
+                    _ => ("WGPUBufferBindingType_Storage", "WGPUBufferUsage_Storage"),
+                };
+                let buffer = if fits {
+                    "state.buffer".to_string()
+                } else {
+                    placeholder_buffer(usage)
+                };
+                let _ = writeln!(
+                    layout_entries,
+                    "    layout_entries[{index}].buffer.type = {ty};"
+                );
+                let _ = write!(
+                    group_entries,
+                    "    group_entries[{index}].buffer = {buffer};\n    \
+                    group_entries[{index}].size = WGPU_WHOLE_SIZE;\n"
+                );
+            }
+            "Texture" => {
+                let view = if fits {
+                    "state.texture_view".to_string()
+                } else {
+                    self.placeholder_texture(
+                        binding,
+                        "WGPUTextureFormat_RGBA8Unorm",
+                        "WGPUTextureUsage_TextureBinding",
+                        placeholders,
+                        releases,
+                    )
+                };
+                let _ = write!(
+                    layout_entries,
+                    "    layout_entries[{index}].texture.sampleType = WGPUTextureSampleType_Float;\n    \
+                    layout_entries[{index}].texture.viewDimension = WGPUTextureViewDimension_2D;\n"
+                );
+                let _ = writeln!(
+                    group_entries,
+                    "    group_entries[{index}].textureView = {view};"
+                );
+            }
+            "Sampler" => {
+                let sampler = if fits {
+                    "state.sampler".to_string()
+                } else {
+                    let _ = write!(
+                        placeholders,
+                        "    // No configured sampler fits binding {binding}\n    \
+                        WGPUSamplerDescriptor binding_{binding}_sampler_descriptor = {{}};\n    \
+                        binding_{binding}_sampler_descriptor.label = str(\"Binding {binding} Sampler\");\n    \
+                        binding_{binding}_sampler_descriptor.magFilter = WGPUFilterMode_Linear;\n    \
+                        binding_{binding}_sampler_descriptor.minFilter = WGPUFilterMode_Linear;\n    \
+                        binding_{binding}_sampler_descriptor.lodMaxClamp = 32.0f;\n    \
+                        binding_{binding}_sampler_descriptor.maxAnisotropy = 1;\n    \
+                        WGPUSampler binding_{binding}_sampler =\n        \
+                            wgpuDeviceCreateSampler(state.device, &binding_{binding}_sampler_descriptor);\n\n"
+                    );
+                    let _ = writeln!(
+                        releases,
+                        "    wgpuSamplerRelease(binding_{binding}_sampler);"
+                    );
+                    format!("binding_{binding}_sampler")
+                };
+                let _ = writeln!(
+                    layout_entries,
+                    "    layout_entries[{index}].sampler.type = WGPUSamplerBindingType_Filtering;"
+                );
+                let _ = writeln!(
+                    group_entries,
+                    "    group_entries[{index}].sampler = {sampler};"
+                );
+            }
+            _ => {
+                let (access, format) = CodeGenerator::storage_texture_binding(entry);
+                let format = texture_format(&format, "Rgba8Unorm");
+                let view = if fits {
+                    "state.texture_view".to_string()
+                } else {
+                    self.placeholder_texture(
+                        binding,
+                        &format,
+                        "WGPUTextureUsage_StorageBinding",
+                        placeholders,
+                        releases,
+                    )
+                };
+                let _ = write!(
+                    layout_entries,
+                    "    layout_entries[{index}].storageTexture.access = WGPUStorageTextureAccess_{:?};\n    \
+                    layout_entries[{index}].storageTexture.format = {format};\n    \
+                    layout_entries[{index}].storageTexture.viewDimension = WGPUTextureViewDimension_2D;\n",
+                    access
+                );
+                let _ = writeln!(
+                    group_entries,
+                    "    group_entries[{index}].textureView = {view};"
+                );
+            }
+        }
+    }
+
+    /// Add a placeholder texture for a binding and return its view
+    fn placeholder_texture(
+        &self,
+        binding: u32,
+        format: &str,
+        usage: &str,
+        placeholders: &mut String,
+        releases: &mut String,
+    ) -> String {
+        let _ = write!(
+            placeholders,
+            "    // No configured texture fits binding {binding}\n    \
+            WGPUTextureDescriptor binding_{binding}_texture_descriptor = {{}};\n    \
+            binding_{binding}_texture_descriptor.label = str(\"Binding {binding} Texture\");\n    \
+            binding_{binding}_texture_descriptor.size = {{{DEFAULT_TEXTURE_WIDTH}, {DEFAULT_TEXTURE_HEIGHT}, 1}};\n    \
+            binding_{binding}_texture_descriptor.mipLevelCount = 1;\n    \
+            binding_{binding}_texture_descriptor.sampleCount = 1;\n    \
+            binding_{binding}_texture_descriptor.dimension = WGPUTextureDimension_2D;\n    \
+            binding_{binding}_texture_descriptor.format = {format};\n    \
+            binding_{binding}_texture_descriptor.usage = {usage};\n    \
+            WGPUTexture binding_{binding}_texture =\n        \
+                wgpuDeviceCreateTexture(state.device, &binding_{binding}_texture_descriptor);\n    \
+            WGPUTextureView binding_{binding}_view = wgpuTextureCreateView(binding_{binding}_texture, nullptr);\n\n"
+        );
+        let _ = write!(
+            releases,
+            "    wgpuTextureViewRelease(binding_{binding}_view);\n    \
+            wgpuTextureRelease(binding_{binding}_texture);\n"
+        );
+        format!("binding_{binding}_view")
+    }
+
+    /// Render pipeline creation code, and its attachments
+    fn render_pipeline_creation(pipeline_state: &RenderPipelinePanelState) -> String {
+        let sample_count = pipeline_state.sample_count.max(1);
+        if !matches!(pipeline_state.polygon_mode.as_str(), "Fill" | "") {
+            log::warn!(
+                "webgpu.h has no polygon modes. Exporting the {} polygon mode as Fill.",
+                pipeline_state.polygon_mode
+            );
+        }
+        let write_mask = match [
+            pipeline_state.write_red,
+            pipeline_state.write_green,
+            pipeline_state.write_blue,
+            pipeline_state.write_alpha,
+        ] {
+            [true, true, true, true] => "WGPUColorWriteMask_All".to_string(),
+            _ => flags(
+                &[
+                    (pipeline_state.write_red, "WGPUColorWriteMask_Red"),
+                    (pipeline_state.write_green, "WGPUColorWriteMask_Green"),
+                    (pipeline_state.write_blue, "WGPUColorWriteMask_Blue"),
+                    (pipeline_state.write_alpha, "WGPUColorWriteMask_Alpha"),
+                ],
+                "WGPUColorWriteMask_None",
+            ),
+        };
+        let front_face = match variant(&pipeline_state.front_face, "Ccw") {
+            "Cw" => "WGPUFrontFace_CW",
+            _ => "WGPUFrontFace_CCW",
+        };
+        let cull_mode = match pipeline_state.cull_mode.as_str() {
+            "Front" => "WGPUCullMode_Front",
+            "Back" => "WGPUCullMode_Back",
+            _ => "WGPUCullMode_None",
+        };
+
+        let mut code = String::new();
+        if pipeline_state.blend_enabled {
+            let _ = write!(
+                code,
+                "    WGPUBlendState blend = {{}};\n    \
+                blend.color.srcFactor = {};\n    \
+                blend.color.dstFactor = {};\n    \
+                blend.color.operation = {};\n    \
+                blend.alpha.srcFactor = {};\n    \
+                blend.alpha.dstFactor = {};\n    \
+                blend.alpha.operation = {};\n",
+                enumerant("BlendFactor", &pipeline_state.color_blend_src, "One"),
+                enumerant("BlendFactor", &pipeline_state.color_blend_dst, "Zero"),
+                enumerant("BlendOperation", &pipeline_state.color_blend_op, "Add"),
+                enumerant("BlendFactor", &pipeline_state.alpha_blend_src, "One"),
+                enumerant("BlendFactor", &pipeline_state.alpha_blend_dst, "Zero"),
+                enumerant("BlendOperation", &pipeline_state.alpha_blend_op, "Add")
+            );
+        }
+        let _ = write!(
+            code,
+            "    // The color target uses the surface format rather than the pipeline\n    \
+            // panel's target format, so the pipeline can draw to the window\n    \
+            WGPUColorTargetState color_target = {{}};\n    \
+            color_target.format = state.config.format;\n    \
+            color_target.blend = {};\n    \
+            color_target.writeMask = {};\n    \
+            WGPUFragmentState fragment = {{}};\n    \
+            fragment.module = state.shader_module;\n    \
+            fragment.entryPoint = {};\n    \
+            fragment.targetCount = 1;\n    \
+            fragment.targets = &color_target;\n",
+            if pipeline_state.blend_enabled {
+                "&blend"
+            } else {
+                "nullptr"
+            },
+            write_mask,
+            entry_point(&pipeline_state.fragment_entry_point)
+        );
+
+        if pipeline_state.enable_depth_stencil {
+            let mask = |input: &str| {
+                u32::from_str_radix(input.trim_start_matches("0x"), 16).unwrap_or(0xFFFFFFFF)
+            };
+            let face = |face: &str, compare: &str, fail: &str, depth_fail: &str, pass: &str| {
+                format!(
+                    "    depth_stencil.{face}.compare = {};\n    \
+                    depth_stencil.{face}.failOp = {};\n    \
+                    depth_stencil.{face}.depthFailOp = {};\n    \
+                    depth_stencil.{face}.passOp = {};\n",
+                    enumerant("CompareFunction", compare, "Always"),
+                    enumerant("StencilOperation", fail, "Keep"),
+                    enumerant("StencilOperation", depth_fail, "Keep"),
+                    enumerant("StencilOperation", pass, "Keep")
+                )
+            };
+            let _ = write!(
+                code,
+                "    WGPUDepthStencilState depth_stencil = {{}};\n    \
+                depth_stencil.format = {};\n    \
+                depth_stencil.depthWriteEnabled = {};\n    \
+                depth_stencil.depthCompare = {};\n\
+                {}{}    \
+                depth_stencil.stencilReadMask = {:#X};\n    \
+                depth_stencil.stencilWriteMask = {:#X};\n",
+                texture_format(&pipeline_state.depth_format, "Depth24Plus"),
+                if pipeline_state.depth_write_enabled {
+                    "WGPUOptionalBool_True"
+                } else {
+                    "WGPUOptionalBool_False"
+                },
+                enumerant("CompareFunction", &pipeline_state.depth_compare, "Less"),
+                face(
+                    "stencilFront",
+                    &pipeline_state.stencil_front_compare,
+                    &pipeline_state.stencil_front_fail_op,
+                    &pipeline_state.stencil_front_depth_fail_op,
+                    &pipeline_state.stencil_front_pass_op
+                ),
+                face(
+                    "stencilBack",
+                    &pipeline_state.stencil_back_compare,
+                    &pipeline_state.stencil_back_fail_op,
+                    &pipeline_state.stencil_back_depth_fail_op,
+                    &pipeline_state.stencil_back_pass_op
+                ),
+                mask(&pipeline_state.stencil_read_mask),
+                mask(&pipeline_state.stencil_write_mask)
+            );
+        }
+
+        let _ = write!(
+            code,
+            "    WGPURenderPipelineDescriptor render_pipeline_descriptor = {{}};\n    \
+            render_pipeline_descriptor.label = str({});\n    \
+            render_pipeline_descriptor.layout = pipeline_layout;\n    \
+            render_pipeline_descriptor.vertex.module = state.shader_module;\n    \
+            render_pipeline_descriptor.vertex.entryPoint = {};\n    \
+            render_pipeline_descriptor.primitive.topology = {};\n    \
+            render_pipeline_descriptor.primitive.frontFace = {};\n    \
+            render_pipeline_descriptor.primitive.cullMode = {};\n    \
+            render_pipeline_descriptor.depthStencil = {};\n    \
+            render_pipeline_descriptor.multisample.count = {};\n    \
+            render_pipeline_descriptor.multisample.mask = 0xFFFFFFFF;\n    \
+            render_pipeline_descriptor.multisample.alphaToCoverageEnabled = {};\n    \
+            render_pipeline_descriptor.fragment = &fragment;\n    \
+            state.render_pipeline =\n        \
+                wgpuDeviceCreateRenderPipeline(state.device, &render_pipeline_descriptor);\n",
+            string_literal(&pipeline_state.label),
+            entry_point(&pipeline_state.vertex_entry_point),
+            enumerant(
+                "PrimitiveTopology",
+                &pipeline_state.topology,
+                "TriangleList"
+            ),
+            front_face,
+            cull_mode,
+            if pipeline_state.enable_depth_stencil {
+                "&depth_stencil"
+            } else {
+                "nullptr"
+            },
+            sample_count,
+            pipeline_state.alpha_to_coverage_enabled && sample_count > 1
+        );
+        code.push_str(&Self::attachment_creation(pipeline_state, "    "));
+        code.push('\n');
+        code
+    }
+
+    /// Creation of the pipeline's depth and multisampled color targets,
+    /// replacing those of the previous size
+    fn attachment_creation(pipeline_state: &RenderPipelinePanelState, indent: &str) -> String {
+        let sample_count = pipeline_state.sample_count.max(1);
+        let mut code = String::new();
+        if pipeline_state.enable_depth_stencil {
+            let _ = writeln!(
+                code,
+                "{indent}state.depth_view = create_attachment(state, {}, {}, \"Depth Texture\");",
+                texture_format(&pipeline_state.depth_format, "Depth24Plus"),
+                sample_count
+            );
+        }
+        if sample_count > 1 {
+            let _ = write!(
+                code,
+                "{indent}state.msaa_view =\n\
+                {indent}    create_attachment(state, state.config.format, {}, \"Multisampled Color Texture\");\n",
+                sample_count
+            );
+        }
+        code
+    }
+
+    /// Compute pipeline creation code
+    fn compute_pipeline_creation(compute_state: &ComputePipelinePanelState) -> String {
+        format!(
+            "    WGPUComputePipelineDescriptor compute_pipeline_descriptor = {{}};\n    \
+            compute_pipeline_descriptor.label = str({});\n    \
+            compute_pipeline_descriptor.layout = pipeline_layout;\n    \
+            compute_pipeline_descriptor.compute.module = state.shader_module;\n    \
+            compute_pipeline_descriptor.compute.entryPoint = {};\n    \
+            state.compute_pipeline =\n        \
+                wgpuDeviceCreateComputePipeline(state.device, &compute_pipeline_descriptor);\n\n",
+            string_literal(&compute_state.label),
+            entry_point(&compute_state.entry_point)
+        )
+    }
+
+    /// `resize`, which reconfigures the surface and recreates the attachments
+    fn resize_function(&self) -> String {
+        let attachments = match CodeGenerator::render_pipeline_state(self.state.as_ref()) {
+            Some(pipeline) => {
+                let mut code = String::new();
+                if pipeline.enable_depth_stencil {
+                    code.push_str("    wgpuTextureViewRelease(state.depth_view);\n");
+                }
+                if pipeline.sample_count > 1 {
+                    code.push_str("    wgpuTextureViewRelease(state.msaa_view);\n");
+                }
+                code.push_str(&Self::attachment_creation(pipeline, "    "));
+                code
+            }
+            None => String::new(),
+        };
+        format!(
+            "/// Reconfigure the surface for the window's new size\n\
+            static void resize(State& state, int width, int height) {{\n    \
+                state.config.width = static_cast<uint32_t>(width);\n    \
+                state.config.height = static_cast<uint32_t>(height);\n    \
+                wgpuSurfaceConfigure(state.surface, &state.config);\n\
+            {}\
+            }}\n\n",
+            attachments
+        )
+    }
+
+    /// `render`, which dispatches the compute pipeline, then clears the
+    /// window and draws a full-screen triangle with the render pipeline
+    fn render_function(&self) -> String {
+        let state = self.state.as_ref();
+        let has_bind_group = !CodeGenerator::bind_group_entries(state).is_empty();
+        let mut code = String::from(
+            "/// Draw a frame and present it\n\
+            static void render(State& state) {\n    \
+                WGPUSurfaceTexture surface_texture = {};\n    \
+                wgpuSurfaceGetCurrentTexture(state.surface, &surface_texture);\n    \
+                switch (surface_texture.status) {\n    \
+                case WGPUSurfaceGetCurrentTextureStatus_SuccessOptimal:\n    \
+                case WGPUSurfaceGetCurrentTextureStatus_SuccessSuboptimal:\n        \
+                    break;\n    \
+                case WGPUSurfaceGetCurrentTextureStatus_Outdated:\n    \
+                case WGPUSurfaceGetCurrentTextureStatus_Lost:\n        \
+                    // Reconfigure the surface and skip the frame\n        \
+                    if (surface_texture.texture) {\n            \
+                        wgpuTextureRelease(surface_texture.texture);\n        \
+                    }\n        \
+                    wgpuSurfaceConfigure(state.surface, &state.config);\n        \
+                    return;\n    \
+                default:\n        \
+                    // Timed out, try again next frame\n        \
+                    if (surface_texture.texture) {\n            \
+                        wgpuTextureRelease(surface_texture.texture);\n        \
+                    }\n        \
+                    return;\n    \
+                }\n    \
+                WGPUTextureView view = wgpuTextureCreateView(surface_texture.texture, nullptr);\n    \
+                WGPUCommandEncoder encoder = wgpuDeviceCreateCommandEncoder(state.device, nullptr);\n\n",
+        );
+
+        // Dispatch the compute pipeline before drawing
+        if CodeGenerator::compute_pipeline_state(state).is_some() {
+            let [x, y, z] = self.config.workgroups.unwrap_or([1, 1, 1]);
+            let _ = write!(
+                code,
+                "    WGPUComputePassEncoder compute_pass = wgpuCommandEncoderBeginComputePass(encoder, nullptr);\n    \
+                wgpuComputePassEncoderSetPipeline(compute_pass, state.compute_pipeline);\n\
+                {}    \
+                wgpuComputePassEncoderDispatchWorkgroups(compute_pass, {}, {}, {});\n    \
+                wgpuComputePassEncoderEnd(compute_pass);\n    \
+                wgpuComputePassEncoderRelease(compute_pass);\n\n",
+                if has_bind_group {
+                    "    wgpuComputePassEncoderSetBindGroup(compute_pass, 0, state.bind_group, 0, nullptr);\n"
+                } else {
+                    ""
+                },
+                x,
+                y,
+                z
+            );
+        }
+
+        // The pass always clears the window, and draws if there's a pipeline
+        let render_pipeline = CodeGenerator::render_pipeline_state(state);
+        let multisampled = render_pipeline.is_some_and(|pipeline| pipeline.sample_count > 1);
+        let _ = write!(
+            code,
+            "    WGPURenderPassColorAttachment color_attachment = {{}};\n    \
+            color_attachment.view = {};\n    \
+            color_attachment.resolveTarget = {};\n    \
+            color_attachment.depthSlice = WGPU_DEPTH_SLICE_UNDEFINED;\n    \
+            color_attachment.loadOp = WGPULoadOp_Clear;\n    \
+            color_attachment.storeOp = {};\n    \
+            color_attachment.clearValue = WGPUColor{{{:.2}, {:.2}, {:.2}, {:.2}}};\n    \
+            WGPURenderPassDescriptor render_pass_descriptor = {{}};\n    \
+            render_pass_descriptor.label = str(\"Render Pass\");\n    \
+            render_pass_descriptor.colorAttachmentCount = 1;\n    \
+            render_pass_descriptor.colorAttachments = &color_attachment;\n",
+            if multisampled {
+                "state.msaa_view"
+            } else {
+                "view"
+            },
+            if multisampled { "view" } else { "nullptr" },
+            if multisampled {
+                "WGPUStoreOp_Discard"
+            } else {
+                "WGPUStoreOp_Store"
+            },
+            self.config.clear_color[0],
+            self.config.clear_color[1],
+            self.config.clear_color[2],
+            self.config.clear_color[3]
+        );
+        if let Some(pipeline) = render_pipeline.filter(|pipeline| pipeline.enable_depth_stencil) {
+            code.push_str(
+                "    WGPURenderPassDepthStencilAttachment depth_attachment = {};\n    \
+                depth_attachment.view = state.depth_view;\n    \
+                depth_attachment.depthLoadOp = WGPULoadOp_Clear;\n    \
+                depth_attachment.depthStoreOp = WGPUStoreOp_Store;\n    \
+                depth_attachment.depthClearValue = 1.0f;\n",
+            );
+            if pipeline.depth_format.contains("Stencil") {
+                code.push_str(
+                    "    depth_attachment.stencilLoadOp = WGPULoadOp_Clear;\n    \
+                    depth_attachment.stencilStoreOp = WGPUStoreOp_Store;\n",
+                );
+            }
+            code.push_str(
+                "    render_pass_descriptor.depthStencilAttachment = &depth_attachment;\n",
+            );
+        }
+        code.push_str(
+            "    WGPURenderPassEncoder render_pass =\n        \
+                wgpuCommandEncoderBeginRenderPass(encoder, &render_pass_descriptor);\n",
+        );
+        if render_pipeline.is_some() {
+            code.push_str(
+                "    wgpuRenderPassEncoderSetPipeline(render_pass, state.render_pipeline);\n",
+            );
+            if has_bind_group {
+                code.push_str(
+                    "    wgpuRenderPassEncoderSetBindGroup(render_pass, 0, state.bind_group, 0, nullptr);\n",
+                );
+            }
+            code.push_str("    wgpuRenderPassEncoderDraw(render_pass, 3, 1, 0, 0);\n");
+        }
+        code.push_str(
+            "    wgpuRenderPassEncoderEnd(render_pass);\n    \
+            wgpuRenderPassEncoderRelease(render_pass);\n\
+            \n    \
+            WGPUCommandBuffer commands = wgpuCommandEncoderFinish(encoder, nullptr);\n    \
+            wgpuQueueSubmit(state.queue, 1, &commands);\n    \
+            wgpuSurfacePresent(state.surface);\n\
+            \n    \
+            wgpuCommandBufferRelease(commands);\n    \
+            wgpuCommandEncoderRelease(encoder);\n    \
+            wgpuTextureViewRelease(view);\n    \
+            wgpuTextureRelease(surface_texture.texture);\n\
+            }\n\n",
+        );
+        code
+    }
+
+    /// `release`, which releases the objects in reverse creation order
+    fn release_function(&self) -> String {
+        let mut code = String::from(
+            "/// Release everything in the reverse order of creation\n\
+            static void release(State& state) {\n",
+        );
+        for (field, ty) in self.resources().into_iter().rev() {
+            let _ = writeln!(
+                code,
+                "    {}Release(state.{});",
+                ty.replacen("WGPU", "wgpu", 1),
+                field
+            );
+        }
+        code.push_str(
+            "    wgpuSurfaceUnconfigure(state.surface);\n    \
+            wgpuQueueRelease(state.queue);\n    \
+            wgpuDeviceRelease(state.device);\n    \
+            wgpuAdapterRelease(state.adapter);\n    \
+            wgpuSurfaceRelease(state.surface);\n    \
+            wgpuInstanceRelease(state.instance);\n\
+            }\n\n",
+        );
+        code
+    }
+
+    /// `main`, which opens the window and renders until it is closed
+    fn main_function(&self) -> String {
+        format!(
+            "int main() {{\n    \
+                if (!glfwInit()) {{\n        \
+                    std::fprintf(stderr, \"Failed to initialize GLFW\\n\");\n        \
+                    return 1;\n    \
+                }}\n    \
+                // WebGPU presents to the window, it needs no OpenGL context\n    \
+                glfwWindowHint(GLFW_CLIENT_API, GLFW_NO_API);\n    \
+                GLFWwindow* window = glfwCreateWindow({}, {}, {}, nullptr, nullptr);\n    \
+                if (!window) {{\n        \
+                    std::fprintf(stderr, \"Failed to open a window\\n\");\n        \
+                    glfwTerminate();\n        \
+                    return 1;\n    \
+                }}\n\
+                \n    \
+                State state;\n    \
+                if (!init(state, window)) {{\n        \
+                    glfwDestroyWindow(window);\n        \
+                    glfwTerminate();\n        \
+                    return 1;\n    \
+                }}\n\
+                \n    \
+                while (!glfwWindowShouldClose(window)) {{\n        \
+                    glfwPollEvents();\n        \
+                    int width = 0;\n        \
+                    int height = 0;\n        \
+                    glfwGetFramebufferSize(window, &width, &height);\n        \
+                    // Minimized windows have nothing to draw to\n        \
+                    if (width == 0 || height == 0) {{\n            \
+                        glfwWaitEvents();\n            \
+                        continue;\n        \
+                    }}\n        \
+                    if (static_cast<uint32_t>(width) != state.config.width ||\n            \
+                        static_cast<uint32_t>(height) != state.config.height) {{\n            \
+                        resize(state, width, height);\n        \
+                    }}\n        \
+                    render(state);\n        \
+                    wgpuInstanceProcessEvents(state.instance);\n    \
+                }}\n\
+                \n    \
+                release(state);\n    \
+                glfwDestroyWindow(window);\n    \
+                glfwTerminate();\n    \
+                return 0;\n\
+            }}\n",
+            self.config.canvas_width,
+            self.config.canvas_height,
+            string_literal(&self.config.project_name)
+        )
+    }
+
+    /// README.md with the build steps
+    fn readme(&self) -> String {
+        format!(
+            "# {}\n\
+            \n\
+            Standalone WebGPU C++ project generated from wgpu_playground. It uses the\n\
+            webgpu.h API and is built against [Dawn](https://dawn.googlesource.com/dawn).\n\
+            \n\
+            ## Building\n\
+            \n\
+            ```bash\n\
+            cmake -B build -DCMAKE_BUILD_TYPE=Release\n\
+            cmake --build build\n\
+            ```\n\
+            \n\
+            The first configure downloads Dawn and its dependencies, which takes a while.\n\
+            To build against a Dawn checkout you already have instead:\n\
+            \n\
+            ```bash\n\
+            cmake -B build -DCMAKE_BUILD_TYPE=Release -DDAWN_SOURCE_DIR=/path/to/dawn\n\
+            ```\n\
+            \n\
+            Pin the downloaded revision with `-DDAWN_GIT_TAG=<commit>` if a newer Dawn\n\
+            changes webgpu.h.\n\
+            \n\
+            ## Running\n\
+            \n\
+            ```bash\n\
+            ./build/{}\n\
+            ```\n\
+            \n\
+            ## Requirements\n\
+            \n\
+            - CMake 3.22+, a C++20 compiler, Git and Python 3\n\
+            - A GPU with WebGPU support (Vulkan, Metal, or DirectX 12)\n\
+            \n\
+            ## Generated Configuration\n\
+            \n\
+            - Canvas size: {}x{}\n\
+            - Clear color: RGB({:.2}, {:.2}, {:.2})\n\
+            \n\
+            ---\n\
+            \n\
+            Generated by wgpu_playground\n",
+            self.config.project_name,
+            self.config.project_name,
+            self.config.canvas_width,
+            self.config.canvas_height,
+            self.config.clear_color[0],
+            self.config.clear_color[1],
+            self.config.clear_color[2]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code_generator::ExportTarget;
+
+    #[test]
+    fn test_texture_format_names() {
+        for (name, expected) in [
+            ("Rgba8UnormSrgb", "WGPUTextureFormat_RGBA8UnormSrgb"),
+            ("Bgra8Unorm", "WGPUTextureFormat_BGRA8Unorm"),
+            ("R32Float", "WGPUTextureFormat_R32Float"),
+            ("Rg16Sint", "WGPUTextureFormat_RG16Sint"),
+            ("Rgb10a2Unorm", "WGPUTextureFormat_RGB10A2Unorm"),
+            ("Rg11b10Ufloat", "WGPUTextureFormat_RG11B10Ufloat"),
+            (
+                "Depth24PlusStencil8",
+                "WGPUTextureFormat_Depth24PlusStencil8",
+            ),
+            ("Stencil8", "WGPUTextureFormat_Stencil8"),
+            ("Bc1RgbaUnormSrgb", "WGPUTextureFormat_BC1RGBAUnormSrgb"),
+            ("Bc4RUnorm", "WGPUTextureFormat_BC4RUnorm"),
+            ("Bc6hRgbUfloat", "WGPUTextureFormat_BC6HRGBUfloat"),
+            ("not a format", "WGPUTextureFormat_RGBA8Unorm"),
+        ] {
+            assert_eq!(texture_format(name, "Rgba8Unorm"), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_string_literal_escapes() {
+        assert_eq!(string_literal("plain"), "\"plain\"");
+        assert_eq!(
+            string_literal("a \"b\"\\c\nd\u{7}"),
+            "\"a \\\"b\\\"\\\\c\\nd\\007\""
+        );
+    }
+
+    #[test]
+    fn test_cpp_project_without_state_draws_triangle() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_cpp_triangle");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let config = CodeGenConfig::new("cpp_triangle".to_string())
+            .with_canvas_size(640, 480)
+            .with_target(ExportTarget::Cpp);
+        CodeGenerator::new(config).generate(&temp_dir).unwrap();
+
+        assert!(temp_dir.join("CMakeLists.txt").exists());
+        assert!(temp_dir.join("README.md").exists());
+        assert!(!temp_dir.join("Cargo.toml").exists());
+        assert!(!temp_dir.join(TEXTURE_DATA_HEADER).exists());
+
+        let cmake = std::fs::read_to_string(temp_dir.join("CMakeLists.txt")).unwrap();
+        assert!(cmake.contains("project(cpp_triangle LANGUAGES C CXX)"));
+        assert!(cmake.contains("dawn::webgpu_dawn"));
+
+        let main_cpp = std::fs::read_to_string(temp_dir.join("main.cpp")).unwrap();
+        assert!(main_cpp.contains("#include <webgpu/webgpu.h>"));
+        assert!(main_cpp.contains("fn vs_main(@builtin(vertex_index) index: u32)"));
+        assert!(
+            main_cpp.contains("render_pipeline_descriptor.vertex.entryPoint = str(\"vs_main\")")
+        );
+        assert!(main_cpp.contains("glfwCreateWindow(640, 480, \"cpp_triangle\""));
+        assert!(main_cpp.contains("wgpuRenderPassEncoderDraw(render_pass, 3, 1, 0, 0)"));
+        assert!(!main_cpp.contains("bind_group"));
+
+        std::fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_cpp_project_rejects_compute() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_cpp_compute");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let config = CodeGenConfig::new("cpp_compute".to_string())
+            .with_example_type(crate::code_generator::ExampleType::Compute)
+            .with_target(ExportTarget::Cpp);
+
+        let error = CodeGenerator::new(config).generate(&temp_dir).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!temp_dir.exists());
+    }
+}
@@ -0,0 +1,3084 @@
+/// Code generation module for exporting playground configuration as standalone Rust project
+use base64::prelude::*;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use naga::{AddressSpace, Scalar, ShaderStage, TypeInner};
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::bind_group::StorageTextureAccess;
+use crate::bind_group_panel::{BindingTypeConfig, STORAGE_TEXTURE_ACCESSES};
+use crate::state::{
+    parse_variant, BindGroupEntryState, BufferPanelState, ComputePipelinePanelState,
+    PlaygroundState, RenderPipelinePanelState, SamplerPanelState, ShaderEditorState,
+    TexturePanelState,
+};
+
+mod cpp;
+mod python;
+
+/// Default buffer size in bytes when parsing fails
+const DEFAULT_BUFFER_SIZE: u64 = 256;
+
+/// Default texture width in pixels when parsing fails
+const DEFAULT_TEXTURE_WIDTH: u32 = 256;
+
+/// Default texture height in pixels when parsing fails
+const DEFAULT_TEXTURE_HEIGHT: u32 = 256;
+
+/// Default texture depth when parsing fails
+const DEFAULT_TEXTURE_DEPTH: u32 = 1;
+
+/// Default compute entry point when no compute pipeline is configured
+const DEFAULT_COMPUTE_ENTRY_POINT: &str = "main";
+
+/// Values printed from the start of the buffer by compute projects
+const PRINTED_VALUES: usize = 16;
+
+/// File next to main.rs holding the RGBA8 pixels of the texture's image
+const TEXTURE_DATA_FILE: &str = "texture.rgba";
+
+/// Binding types of the saved bind group entries the generator handles
+const KNOWN_BINDING_TYPES: [&str; 5] = [
+    "UniformBuffer",
+    "StorageBuffer",
+    "Texture",
+    "Sampler",
+    "StorageTexture",
+];
+
+/// Compute shader used when none is given: doubles every value
+const DEFAULT_COMPUTE_SHADER: &str = "@group(0) @binding(0)
+var<storage, read_write> data: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x < arrayLength(&data)) {
+        data[id.x] = data[id.x] * 2.0;
+    }
+}
+";
+
+/// Shader the C++ and Python projects draw without playground state: a
+/// triangle colored per vertex, which needs no vertex buffers
+const DEFAULT_TRIANGLE_SHADER: &str = "struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(0.0, 0.5),
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, -0.5),
+    );
+    var colors = array<vec3<f32>, 3>(
+        vec3<f32>(1.0, 0.0, 0.0),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(0.0, 0.0, 1.0),
+    );
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[index], 0.0, 1.0);
+    out.color = colors[index];
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+";
+
+/// Window event handler of the generated event loops, indented for a call
+/// at the top level of a function or async block
+const EVENT_HANDLER: &str = "move |event, control_flow| match event {\n            \
+        winit::event::Event::WindowEvent {\n                \
+            ref event,\n                \
+            window_id,\n            \
+        } if window_id == state.window.id() => match event {\n                \
+            WindowEvent::CloseRequested => control_flow.exit(),\n                \
+            WindowEvent::Resized(physical_size) => {\n                    \
+                state.resize(*physical_size);\n                \
+            }\n                \
+            WindowEvent::RedrawRequested => state.render(),\n                \
+            _ => {}\n            \
+        },\n            \
+        winit::event::Event::AboutToWait => {\n                \
+            state.window.request_redraw();\n            \
+        }\n            \
+        _ => {}\n        \
+    }";
+
+/// Start of the generated `render` methods: acquires the frame's surface
+/// texture, reconfiguring the surface and skipping the frame when it's
+/// outdated or lost
+const ACQUIRE_FRAME: &str = "        let output = match self.surface.get_current_texture() {\n            \
+            wgpu::CurrentSurfaceTexture::Success(frame)\n            \
+            | wgpu::CurrentSurfaceTexture::Suboptimal(frame) => frame,\n            \
+            wgpu::CurrentSurfaceTexture::Outdated | wgpu::CurrentSurfaceTexture::Lost => {\n                \
+                self.resize(self.size);\n                \
+                return;\n            \
+            }\n            \
+            // Timed out or occluded, try again next frame\n            \
+            _ => return,\n        \
+        };\n";
+
+/// Helper of the generated playground projects creating the depth and
+/// multisampled color targets at the surface size
+const ATTACHMENT_HELPER: &str = "/// Render target matching the surface size\n\
+    fn create_attachment(\n    \
+        device: &wgpu::Device,\n    \
+        config: &wgpu::SurfaceConfiguration,\n    \
+        format: wgpu::TextureFormat,\n    \
+        sample_count: u32,\n    \
+        label: &str,\n\
+    ) -> wgpu::TextureView {\n    \
+        device\n        \
+            .create_texture(&wgpu::TextureDescriptor {\n            \
+                label: Some(label),\n            \
+                size: wgpu::Extent3d {\n                \
+                    width: config.width,\n                \
+                    height: config.height,\n                \
+                    depth_or_array_layers: 1,\n            \
+                },\n            \
+                mip_level_count: 1,\n            \
+                sample_count,\n            \
+                dimension: wgpu::TextureDimension::D2,\n            \
+                format,\n            \
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,\n            \
+                view_formats: &[],\n        \
+            })\n        \
+            .create_view(&wgpu::TextureViewDescriptor::default())\n\
+    }\n\
+    \n";
+
+/// Rust enum variant for a saved `Debug` name, or `default` if the name
+/// isn't a plain variant name
+fn variant<'a>(name: &'a str, default: &'a str) -> &'a str {
+    if name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        name
+    } else {
+        log::warn!("Unknown variant '{}'. Using {}.", name, default);
+        default
+    }
+}
+
+/// Parse a panel's text input, warning and using `default` if it's invalid
+fn parse_or<T>(input: &str, default: T, what: &str) -> T
+where
+    T: std::str::FromStr + std::fmt::Display,
+    T::Err: std::fmt::Display,
+{
+    input
+        .trim()
+        .parse()
+        .inspect_err(|e| {
+            log::warn!(
+                "Failed to parse {} '{}': {}. Using default {}.",
+                what,
+                input,
+                e,
+                default
+            )
+        })
+        .unwrap_or(default)
+}
+
+/// Whether a texture format can be sampled as a filterable float texture
+fn is_filterable_format(format: &str) -> bool {
+    !["Uint", "Sint", "Depth", "Stencil", "32Float"]
+        .iter()
+        .any(|kind| format.contains(kind))
+}
+
+/// Configuration for code generation
+#[derive(Debug, Clone)]
+pub struct CodeGenConfig {
+    /// Project name
+    pub project_name: String,
+    /// Shader source code (WGSL)
+    pub shader_source: Option<String>,
+    /// Example type being exported
+    pub example_type: ExampleType,
+    /// Canvas dimensions
+    pub canvas_width: u32,
+    pub canvas_height: u32,
+    /// Clear color [r, g, b, a]
+    pub clear_color: [f32; 4],
+    /// Full playground state (optional, for advanced export)
+    pub playground_state: Option<PlaygroundState>,
+    /// Workgroups dispatched by compute projects; `None` dispatches enough
+    /// workgroups along X to cover the buffer
+    pub workgroups: Option<[u32; 3]>,
+    /// Platform the project is built for
+    pub target: ExportTarget,
+}
+
+/// Type of example to generate
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExampleType {
+    /// Basic triangle rendering
+    Triangle,
+    /// 3D rotating cube
+    Cube,
+    /// Custom shader code
+    Custom,
+    /// Compute dispatch on a storage buffer, printing the results
+    Compute,
+}
+
+/// Platform an exported project is built for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// Desktop binary rendering into a window
+    #[default]
+    Native,
+    /// Library rendering into a canvas in the browser, built with wasm-pack,
+    /// that also runs natively
+    Web,
+    /// C++ project using the webgpu.h API, built with CMake against Dawn
+    Cpp,
+    /// Python script using wgpu-py, rendering into a rendercanvas window
+    Python,
+}
+
+impl Default for CodeGenConfig {
+    fn default() -> Self {
+        Self {
+            project_name: "wgpu_standalone".to_string(),
+            shader_source: None,
+            example_type: ExampleType::Triangle,
+            canvas_width: 800,
+            canvas_height: 600,
+            clear_color: [0.1, 0.1, 0.1, 1.0],
+            playground_state: None,
+            workgroups: None,
+            target: ExportTarget::Native,
+        }
+    }
+}
+
+impl CodeGenConfig {
+    /// Create a new configuration
+    pub fn new(project_name: String) -> Self {
+        Self {
+            project_name,
+            ..Default::default()
+        }
+    }
+
+    /// Set shader source
+    pub fn with_shader(mut self, source: String) -> Self {
+        self.shader_source = Some(source);
+        self
+    }
+
+    /// Set example type
+    pub fn with_example_type(mut self, example_type: ExampleType) -> Self {
+        self.example_type = example_type;
+        self
+    }
+
+    /// Set canvas dimensions
+    pub fn with_canvas_size(mut self, width: u32, height: u32) -> Self {
+        self.canvas_width = width;
+        self.canvas_height = height;
+        self
+    }
+
+    /// Set clear color
+    pub fn with_clear_color(mut self, color: [f32; 4]) -> Self {
+        self.clear_color = color;
+        self
+    }
+
+    /// Set playground state for advanced export
+    pub fn with_playground_state(mut self, state: PlaygroundState) -> Self {
+        self.playground_state = Some(state);
+        self
+    }
+
+    /// Set the workgroups dispatched by compute projects
+    pub fn with_workgroups(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.workgroups = Some([x, y, z]);
+        self
+    }
+
+    /// Set the platform the project is built for
+    pub fn with_target(mut self, target: ExportTarget) -> Self {
+        self.target = target;
+        self
+    }
+}
+
+/// Storage buffer used by a compute entry point
+struct StorageBinding {
+    group: u32,
+    binding: u32,
+    /// Rust type of the buffer's scalars, `u32` for structs
+    element: &'static str,
+}
+
+/// Shader, pipeline, buffer and dispatch of a compute project
+struct ComputeSetup<'a> {
+    shader_source: &'a str,
+    pipeline_label: String,
+    entry_point: String,
+    buffer_label: String,
+    buffer_size: u64,
+    /// `wgpu::BufferUsages` flags of the buffer, e.g. `STORAGE`
+    usage: Vec<&'static str>,
+    /// Where the buffer is bound, `None` if the entry point uses no storage
+    binding: Option<StorageBinding>,
+    workgroups: [u32; 3],
+}
+
+/// Workgroup size of a compute entry point and the first storage buffer it uses
+fn reflect_compute_entry_point(
+    source: &str,
+    entry_point: &str,
+) -> Result<([u32; 3], Option<StorageBinding>), String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|e| e.emit_to_string(source))?;
+    let (index, entry) = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .find(|(_, e)| e.name == entry_point && e.stage == ShaderStage::Compute)
+        .ok_or_else(|| format!("The shader has no compute entry point '{}'", entry_point))?;
+
+    let uses = info.get_entry_point(index);
+    let binding = module.global_variables.iter().find_map(|(handle, var)| {
+        if !matches!(var.space, AddressSpace::Storage { .. }) || uses[handle].is_empty() {
+            return None;
+        }
+        let resource = var.binding.as_ref()?;
+        let element = match module.types[var.ty].inner {
+            TypeInner::Array { base, .. } => base,
+            _ => var.ty,
+        };
+        let scalar = match module.types[element].inner {
+            TypeInner::Scalar(scalar) | TypeInner::Vector { scalar, .. } => Some(scalar),
+            TypeInner::Matrix { scalar, .. } => Some(scalar),
+            _ => None,
+        };
+        let element = match scalar {
+            Some(Scalar::F32) => "f32",
+            Some(Scalar::I32) => "i32",
+            _ => "u32",
+        };
+        Some(StorageBinding {
+            group: resource.group,
+            binding: resource.binding,
+            element,
+        })
+    });
+    Ok((entry.workgroup_size, binding))
+}
+
+/// Code generator for creating standalone Rust projects
+pub struct CodeGenerator {
+    config: CodeGenConfig,
+}
+
+impl CodeGenerator {
+    /// Create a new code generator
+    pub fn new(config: CodeGenConfig) -> Self {
+        Self { config }
+    }
+
+    /// Generate the complete project structure
+    pub fn generate(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        // Reject a compute shader that can't be exported before writing files
+        if self.config.example_type == ExampleType::Compute {
+            match self.config.target {
+                ExportTarget::Native | ExportTarget::Python => {}
+                ExportTarget::Web => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Compute projects can only be exported for native targets, \
+                         they block on reading the buffer back",
+                    ))
+                }
+                ExportTarget::Cpp => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Compute projects can only be exported as Rust or Python projects",
+                    ))
+                }
+            }
+            Self::compute_setup(&self.config)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        }
+
+        // C++ and Python projects share nothing with the Cargo project below
+        match self.config.target {
+            ExportTarget::Cpp => return cpp::CppGenerator::new(&self.config).generate(output_dir),
+            ExportTarget::Python => {
+                return python::PythonGenerator::new(&self.config).generate(output_dir)
+            }
+            ExportTarget::Native | ExportTarget::Web => {}
+        }
+
+        // Create project directory
+        std::fs::create_dir_all(output_dir)?;
+
+        // Generate Cargo.toml
+        self.generate_cargo_toml(output_dir)?;
+
+        // Generate main.rs
+        self.generate_main_rs(output_dir)?;
+
+        // Write the texture image the playground export includes
+        if let (ExampleType::Triangle | ExampleType::Cube | ExampleType::Custom, Some(state)) =
+            (&self.config.example_type, &self.config.playground_state)
+        {
+            self.generate_texture_data(output_dir, state)?;
+        }
+
+        // Generate shader file if custom shader
+        if let Some(ref shader_source) = self.config.shader_source {
+            self.generate_shader_file(output_dir, shader_source)?;
+        }
+
+        // Generate the page and build script of web projects
+        if self.config.target == ExportTarget::Web {
+            self.generate_index_html(output_dir)?;
+            self.generate_build_script(output_dir)?;
+        }
+
+        // Generate README
+        self.generate_readme(output_dir)?;
+
+        Ok(())
+    }
+
+    /// Generate Cargo.toml file
+    fn generate_cargo_toml(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        let cargo_content = self.get_cargo_toml_content();
+        std::fs::write(output_dir.join("Cargo.toml"), cargo_content)
+    }
+
+    fn get_cargo_toml_content(&self) -> String {
+        if self.config.example_type == ExampleType::Compute {
+            // Compute projects run headless
+            return format!(
+                "[package]\n\
+                name = \"{}\"\n\
+                version = \"0.1.0\"\n\
+                edition = \"2021\"\n\
+                \n\
+                [dependencies]\n\
+                wgpu = \"29.0\"\n\
+                pollster = \"0.4\"\n\
+                env_logger = \"0.11\"\n\
+                log = \"0.4\"\n\
+                bytemuck = {{ version = \"1.19\", features = [\"derive\"] }}\n\
+                \n\
+                [[bin]]\n\
+                name = \"{}\"\n\
+                path = \"src/main.rs\"\n",
+                self.config.project_name, self.config.project_name
+            );
+        }
+        if self.config.target == ExportTarget::Web {
+            // A library for wasm-pack, with native-only and browser-only
+            // dependencies split by target
+            return format!(
+                "[package]\n\
+                name = \"{}\"\n\
+                version = \"0.1.0\"\n\
+                edition = \"2021\"\n\
+                \n\
+                [lib]\n\
+                crate-type = [\"cdylib\", \"rlib\"]\n\
+                \n\
+                [dependencies]\n\
+                wgpu = \"29.0\"\n\
+                winit = {{ version = \"0.30\", default-features = false, features = [\"rwh_06\", \"x11\"] }}\n\
+                log = \"0.4\"\n\
+                bytemuck = {{ version = \"1.19\", features = [\"derive\"] }}\n\
+                \n\
+                [target.'cfg(not(target_arch = \"wasm32\"))'.dependencies]\n\
+                pollster = \"0.4\"\n\
+                env_logger = \"0.11\"\n\
+                \n\
+                [target.'cfg(target_arch = \"wasm32\")'.dependencies]\n\
+                wasm-bindgen = \"0.2\"\n\
+                wasm-bindgen-futures = \"0.4\"\n\
+                console_error_panic_hook = \"0.1\"\n\
+                web-sys = {{ version = \"0.3\", features = [\"Document\", \"Window\", \"Element\", \"HtmlCanvasElement\"] }}\n\
+                \n\
+                [[bin]]\n\
+                name = \"{}\"\n\
+                path = \"src/main.rs\"\n",
+                self.config.project_name, self.config.project_name
+            );
+        }
+        format!(
+            "[package]\n\
+            name = \"{}\"\n\
+            version = \"0.1.0\"\n\
+            edition = \"2021\"\n\
+            \n\
+            [dependencies]\n\
+            wgpu = \"29.0\"\n\
+            winit = {{ version = \"0.30\", default-features = false, features = [\"rwh_06\", \"x11\"] }}\n\
+            pollster = \"0.4\"\n\
+            env_logger = \"0.11\"\n\
+            log = \"0.4\"\n\
+            bytemuck = {{ version = \"1.19\", features = [\"derive\"] }}\n\
+            \n\
+            [[bin]]\n\
+            name = \"{}\"\n\
+            path = \"src/main.rs\"\n",
+            self.config.project_name, self.config.project_name
+        )
+    }
+
+    /// Generate main.rs file
+    fn generate_main_rs(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        let src_dir = output_dir.join("src");
+        std::fs::create_dir_all(&src_dir)?;
+
+        let content = match (&self.config.example_type, &self.config.playground_state) {
+            // Reads the buffer and pipeline from the playground state itself
+            (ExampleType::Compute, _) => self
+                .generate_compute_main()
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?,
+            // Generate advanced main.rs based on playground state
+            (_, Some(playground_state)) => self.generate_playground_main(playground_state),
+            // Generate simple example main.rs
+            (ExampleType::Triangle, None) => self.generate_triangle_main(),
+            (ExampleType::Cube, None) => self.generate_cube_main(),
+            (ExampleType::Custom, None) => self.generate_custom_main(),
+        };
+
+        match self.config.target {
+            ExportTarget::Native => std::fs::write(src_dir.join("main.rs"), content),
+            // The program becomes a library that main.rs and the page both run
+            ExportTarget::Web => {
+                std::fs::write(src_dir.join("lib.rs"), content)?;
+                std::fs::write(src_dir.join("main.rs"), self.generate_web_main())
+            }
+            ExportTarget::Cpp | ExportTarget::Python => {
+                unreachable!("C++ and Python projects have their own generators")
+            }
+        }
+    }
+
+    /// Get default shader source
+    fn get_default_shader_source(&self) -> &str {
+        self.config.shader_source.as_deref().unwrap_or(
+            "struct VertexInput {\n\
+                @location(0) position: vec3<f32>,\n\
+                @location(1) color: vec3<f32>,\n\
+            }\n\
+            \n\
+            struct VertexOutput {\n\
+                @builtin(position) clip_position: vec4<f32>,\n\
+                @location(0) color: vec3<f32>,\n\
+            }\n\
+            \n\
+            @vertex\n\
+            fn vs_main(in: VertexInput) -> VertexOutput {\n\
+                var out: VertexOutput;\n\
+                out.clip_position = vec4<f32>(in.position, 1.0);\n\
+                out.color = in.color;\n\
+                return out;\n\
+            }\n\
+            \n\
+            @fragment\n\
+            fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {\n\
+                return vec4<f32>(in.color, 1.0);\n\
+            }",
+        )
+    }
+
+    /// Generate triangle example main.rs
+    fn generate_triangle_main(&self) -> String {
+        let shader_source = self.get_default_shader_source();
+
+        let mut code = format!(
+            "use std::sync::Arc;\n\
+            use winit::{{\n    \
+                event::*,\n    \
+                event_loop::EventLoop,\n    \
+                window::Window,\n\
+            }};\n\
+            \n\
+            const SHADER_SOURCE: &str = r#\"{}\"#;\n\
+            \n\
+            #[repr(C)]\n\
+            #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]\n\
+            struct Vertex {{\n    \
+                position: [f32; 3],\n    \
+                color: [f32; 3],\n\
+            }}\n\
+            \n\
+            struct State {{\n    \
+                surface: wgpu::Surface<'static>,\n    \
+                device: wgpu::Device,\n    \
+                queue: wgpu::Queue,\n    \
+                config: wgpu::SurfaceConfiguration,\n    \
+                size: winit::dpi::PhysicalSize<u32>,\n    \
+                window: Arc<Window>,\n    \
+                pipeline: wgpu::RenderPipeline,\n    \
+                vertex_buffer: wgpu::Buffer,\n\
+            }}\n\
+            \n\
+            impl State {{\n    \
+                async fn new(window: Arc<Window>) -> Self {{\n        \
+                    let size = window.inner_size();\n\
+                    \n        \
+                    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {{\n            \
+                        backends: wgpu::Backends::all(),\n            \
+                        ..wgpu::InstanceDescriptor::new_without_display_handle()\n        \
+                    }});\n\
+                    \n        \
+                    let surface = instance.create_surface(window.clone()).unwrap();\n\
+                    \n        \
+                    let adapter = instance\n            \
+                        .request_adapter(&wgpu::RequestAdapterOptions {{\n                \
+                            power_preference: wgpu::PowerPreference::default(),\n                \
+                            compatible_surface: Some(&surface),\n                \
+                            force_fallback_adapter: false,\n            \
+                        }})\n            \
+                        .await\n            \
+                        .unwrap();\n\
+                    \n        \
+                    let (device, queue) = adapter\n            \
+                        .request_device(\n                \
+                            &wgpu::DeviceDescriptor {{\n                    \
+                                required_features: wgpu::Features::empty(),\n                    \
+                                required_limits: wgpu::Limits::default(),\n                    \
+                                label: Some(\"Device\"),\n                    \
+                                memory_hints: Default::default(),\n                    \
+                                experimental_features: Default::default(),\n                    \
+                                trace: wgpu::Trace::Off,\n                \
+                            }}\n            \
+                        )\n            \
+                        .await\n            \
+                        .unwrap();\n\
+                    \n        \
+                    let surface_caps = surface.get_capabilities(&adapter);\n        \
+                    let surface_format = surface_caps\n            \
+                        .formats\n            \
+                        .iter()\n            \
+                        .find(|f| f.is_srgb())\n            \
+                        .copied()\n            \
+                        .unwrap_or(surface_caps.formats[0]);\n\
+                    \n        \
+                    let config = wgpu::SurfaceConfiguration {{\n            \
+                        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,\n            \
+                        format: surface_format,\n            \
+                        width: size.width,\n            \
+                        height: size.height,\n            \
+                        present_mode: wgpu::PresentMode::Fifo,\n            \
+                        alpha_mode: surface_caps.alpha_modes[0],\n            \
+                        view_formats: vec![],\n            \
+                        desired_maximum_frame_latency: 2,\n        \
+                    }};\n        \
+                    surface.configure(&device, &config);\n\
+                    \n        \
+                    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {{\n            \
+                        label: Some(\"Shader\"),\n            \
+                        source: wgpu::ShaderSource::Wgsl(SHADER_SOURCE.into()),\n        \
+                    }});\n\
+                    \n        \
+                    let vertices = [\n            \
+                        Vertex {{\n                \
+                            position: [0.0, 0.5, 0.0],\n                \
+                            color: [1.0, 0.0, 0.0],\n            \
+                        }},\n            \
+                        Vertex {{\n                \
+                            position: [-0.5, -0.5, 0.0],\n                \
+                            color: [0.0, 1.0, 0.0],\n            \
+                        }},\n            \
+                        Vertex {{\n                \
+                            position: [0.5, -0.5, 0.0],\n                \
+                            color: [0.0, 0.0, 1.0],\n            \
+                        }},\n        \
+                    ];\n\
+                    \n        \
+                    let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {{\n            \
+                        label: Some(\"Vertex Buffer\"),\n            \
+                        size: std::mem::size_of_val(&vertices) as u64,\n            \
+                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,\n            \
+                        mapped_at_creation: false,\n        \
+                    }});\n\
+                    \n        \
+                    queue.write_buffer(&vertex_buffer, 0, bytemuck::cast_slice(&vertices));\n\
+                    \n        \
+                    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {{\n            \
+                        label: Some(\"Pipeline Layout\"),\n            \
+                        bind_group_layouts: &[],\n            \
+                        immediate_size: 0,\n        \
+                    }});\n\
+                    \n        \
+                    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {{\n            \
+                        label: Some(\"Render Pipeline\"),\n            \
+                        layout: Some(&pipeline_layout),\n            \
+                        vertex: wgpu::VertexState {{\n                \
+                            module: &shader,\n                \
+                            entry_point: Some(\"vs_main\"),\n                \
+                            buffers: &[wgpu::VertexBufferLayout {{\n                    \
+                                array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,\n                    \
+                                step_mode: wgpu::VertexStepMode::Vertex,\n                    \
+                                attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3],\n                \
+                            }}],\n                \
+                            compilation_options: Default::default(),\n            \
+                        }},\n            \
+                        fragment: Some(wgpu::FragmentState {{\n                \
+                            module: &shader,\n                \
+                            entry_point: Some(\"fs_main\"),\n                \
+                            targets: &[Some(wgpu::ColorTargetState {{\n                    \
+                                format: config.format,\n                    \
+                                blend: Some(wgpu::BlendState::REPLACE),\n                    \
+                                write_mask: wgpu::ColorWrites::ALL,\n                \
+                            }})],\n                \
+                            compilation_options: Default::default(),\n            \
+                        }}),\n            \
+                        primitive: wgpu::PrimitiveState {{\n                \
+                            topology: wgpu::PrimitiveTopology::TriangleList,\n                \
+                            strip_index_format: None,\n                \
+                            front_face: wgpu::FrontFace::Ccw,\n                \
+                            cull_mode: Some(wgpu::Face::Back),\n                \
+                            polygon_mode: wgpu::PolygonMode::Fill,\n                \
+                            unclipped_depth: false,\n                \
+                            conservative: false,\n            \
+                        }},\n            \
+                        depth_stencil: None,\n            \
+                        multisample: wgpu::MultisampleState {{\n                \
+                            count: 1,\n                \
+                            mask: !0,\n                \
+                            alpha_to_coverage_enabled: false,\n            \
+                        }},\n            \
+                        multiview_mask: None,\n            \
+                        cache: None,\n        \
+                    }});\n\
+                    \n        \
+                    Self {{\n            \
+                        window,\n            \
+                        surface,\n            \
+                        device,\n            \
+                        queue,\n            \
+                        config,\n            \
+                        size,\n            \
+                        pipeline,\n            \
+                        vertex_buffer,\n        \
+                    }}\n    \
+                }}\n\
+                \n    \
+                fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {{\n        \
+                    if new_size.width > 0 && new_size.height > 0 {{\n            \
+                        self.size = new_size;\n            \
+                        self.config.width = new_size.width;\n            \
+                        self.config.height = new_size.height;\n            \
+                        self.surface.configure(&self.device, &self.config);\n        \
+                    }}\n    \
+                }}\n\
+                \n    \
+                fn render(&mut self) {{\n\
+                {}        \
+                    let view = output\n            \
+                        .texture\n            \
+                        .create_view(&wgpu::TextureViewDescriptor::default());\n\
+                    \n        \
+                    let mut encoder = self\n            \
+                        .device\n            \
+                        .create_command_encoder(&wgpu::CommandEncoderDescriptor {{\n                \
+                            label: Some(\"Render Encoder\"),\n            \
+                        }});\n\
+                    \n        \
+                    {{\n            \
+                        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {{\n                \
+                            label: Some(\"Render Pass\"),\n                \
+                            color_attachments: &[Some(wgpu::RenderPassColorAttachment {{\n                    \
+                                view: &view,\n                    \
+                                resolve_target: None,\n                    \
+                                ops: wgpu::Operations {{\n                        \
+                                    load: wgpu::LoadOp::Clear(wgpu::Color {{\n                            \
+                                        r: {:.2},\n                            \
+                                        g: {:.2},\n                            \
+                                        b: {:.2},\n                            \
+                                        a: {:.2},\n                        \
+                                    }}),\n                        \
+                                    store: wgpu::StoreOp::Store,\n                    \
+                                }},\n                    \
+                                depth_slice: None,\n                \
+                            }})],\n                \
+                            depth_stencil_attachment: None,\n                \
+                            occlusion_query_set: None,\n                \
+                            timestamp_writes: None,\n                \
+                            multiview_mask: None,\n            \
+                        }});\n\
+                        \n            \
+                        render_pass.set_pipeline(&self.pipeline);\n            \
+                        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));\n            \
+                        render_pass.draw(0..3, 0..1);\n        \
+                    }}\n\
+                    \n        \
+                    self.queue.submit(std::iter::once(encoder.finish()));\n        \
+                    output.present();\n    \
+                }}\n\
+            }}\n\
+            \n",
+            shader_source,
+            ACQUIRE_FRAME,
+            self.config.clear_color[0],
+            self.config.clear_color[1],
+            self.config.clear_color[2],
+            self.config.clear_color[3]
+        );
+        code.push_str(&self.generate_main_function());
+        code
+    }
+
+    /// Generate cube example main.rs
+    fn generate_cube_main(&self) -> String {
+        // For now, similar to triangle but we can expand this later
+        self.generate_triangle_main()
+    }
+
+    /// Generate custom shader main.rs
+    fn generate_custom_main(&self) -> String {
+        self.generate_triangle_main()
+    }
+
+    /// Resolve the shader, pipeline, buffer and dispatch of a compute project
+    ///
+    /// The shader comes from the config (a doubling shader by default), the
+    /// entry point and pipeline label from the compute pipeline panel and the
+    /// buffer from the buffer panel of the playground state.
+    fn compute_setup(config: &CodeGenConfig) -> Result<ComputeSetup<'_>, String> {
+        let state = config.playground_state.as_ref();
+        let shader_source = config
+            .shader_source
+            .as_deref()
+            .unwrap_or(DEFAULT_COMPUTE_SHADER);
+        let pipeline = state.and_then(|s| s.compute_pipeline_panel.as_ref());
+        let entry_point = pipeline
+            .map(|p| p.entry_point.as_str())
+            .filter(|e| !e.is_empty())
+            .unwrap_or(DEFAULT_COMPUTE_ENTRY_POINT)
+            .to_string();
+        let pipeline_label = pipeline
+            .map(|p| p.label.as_str())
+            .filter(|l| !l.is_empty())
+            .unwrap_or("Compute Pipeline")
+            .to_string();
+
+        let buffer = state.and_then(|s| s.buffer_panel.as_ref());
+        let buffer_label = buffer
+            .map(|b| b.label.as_str())
+            .filter(|l| !l.is_empty())
+            .unwrap_or("Storage Buffer")
+            .to_string();
+        let size = buffer
+            .and_then(|b| b.size.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_BUFFER_SIZE);
+        // Buffer writes and copies work in multiples of 4 bytes
+        let buffer_size = size.max(1).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+
+        // The buffer is written, read back and bound as storage in any case
+        let mut usage_flags = vec!["STORAGE", "COPY_SRC", "COPY_DST"];
+        if let Some(buffer) = buffer {
+            for (enabled, flag) in [
+                (buffer.usage_vertex, "VERTEX"),
+                (buffer.usage_index, "INDEX"),
+                (buffer.usage_uniform, "UNIFORM"),
+                (buffer.usage_indirect, "INDIRECT"),
+            ] {
+                if enabled {
+                    usage_flags.push(flag);
+                }
+            }
+        }
+
+        let (workgroup_size, binding) = reflect_compute_entry_point(shader_source, &entry_point)?;
+        // One invocation per 4-byte value of the buffer
+        let workgroups = config.workgroups.unwrap_or_else(|| {
+            let values = (buffer_size / 4) as u32;
+            [values.div_ceil(workgroup_size[0]).clamp(1, 65535), 1, 1]
+        });
+
+        Ok(ComputeSetup {
+            shader_source,
+            pipeline_label,
+            entry_point,
+            buffer_label,
+            buffer_size,
+            usage: usage_flags,
+            binding,
+            workgroups,
+        })
+    }
+
+    /// Generate a headless main.rs that runs the compute dispatch and
+    /// prints the buffer
+    fn generate_compute_main(&self) -> Result<String, String> {
+        let setup = Self::compute_setup(&self.config)?;
+        let element = setup.binding.as_ref().map_or("u32", |b| b.element);
+        let mut code = format!(
+            "//! Compute dispatch exported from wgpu_playground\n\
+            \n\
+            const SHADER_SOURCE: &str = r#\"{}\"#;\n\
+            \n\
+            /// Size of the buffer in bytes\n\
+            const BUFFER_SIZE: u64 = {};\n\
+            /// Workgroups dispatched in X, Y and Z\n\
+            const WORKGROUPS: [u32; 3] = [{}, {}, {}];\n\
+            /// Values printed from the start of the buffer\n\
+            const PRINTED_VALUES: usize = {};\n\
+            \n\
+            fn main() {{\n    \
+                env_logger::init();\n    \
+                pollster::block_on(run());\n\
+            }}\n\
+            \n\
+            async fn run() {{\n    \
+                let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {{\n        \
+                    backends: wgpu::Backends::all(),\n        \
+                    ..wgpu::InstanceDescriptor::new_without_display_handle()\n    \
+                }});\n\
+                \n    \
+                let adapter = instance\n        \
+                    .request_adapter(&wgpu::RequestAdapterOptions {{\n            \
+                        power_preference: wgpu::PowerPreference::default(),\n            \
+                        compatible_surface: None,\n            \
+                        force_fallback_adapter: false,\n        \
+                    }})\n        \
+                    .await\n        \
+                    .expect(\"No GPU adapter found\");\n    \
+                println!(\"Running on {{}}\", adapter.get_info().name);\n\
+                \n    \
+                let (device, queue) = adapter\n        \
+                    .request_device(&wgpu::DeviceDescriptor {{\n            \
+                        required_features: wgpu::Features::empty(),\n            \
+                        required_limits: wgpu::Limits::default(),\n            \
+                        label: Some(\"Device\"),\n            \
+                        memory_hints: Default::default(),\n            \
+                        experimental_features: Default::default(),\n            \
+                        trace: wgpu::Trace::Off,\n        \
+                    }})\n        \
+                    .await\n        \
+                    .unwrap();\n\
+                \n    \
+                let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {{\n        \
+                    label: Some(\"Compute Shader\"),\n        \
+                    source: wgpu::ShaderSource::Wgsl(SHADER_SOURCE.into()),\n    \
+                }});\n\
+                \n    \
+                // Fill the buffer with 0, 1, 2, ...\n    \
+                let input: Vec<{}> = (0..BUFFER_SIZE / 4).map(|i| i as {}).collect();\n    \
+                let buffer = device.create_buffer(&wgpu::BufferDescriptor {{\n        \
+                    label: Some(\"{}\"),\n        \
+                    size: BUFFER_SIZE,\n        \
+                    usage: {},\n        \
+                    mapped_at_creation: false,\n    \
+                }});\n    \
+                queue.write_buffer(&buffer, 0, bytemuck::cast_slice(&input));\n\
+                \n    \
+                let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {{\n        \
+                    label: Some(\"Readback Buffer\"),\n        \
+                    size: BUFFER_SIZE,\n        \
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,\n        \
+                    mapped_at_creation: false,\n    \
+                }});\n\
+                \n    \
+                // The layout is derived from the shader\n    \
+                let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {{\n        \
+                    label: Some(\"{}\"),\n        \
+                    layout: None,\n        \
+                    module: &shader_module,\n        \
+                    entry_point: Some(\"{}\"),\n        \
+                    compilation_options: Default::default(),\n        \
+                    cache: None,\n    \
+                }});\n\n",
+            setup.shader_source,
+            setup.buffer_size,
+            setup.workgroups[0],
+            setup.workgroups[1],
+            setup.workgroups[2],
+            PRINTED_VALUES,
+            element,
+            element,
+            setup.buffer_label,
+            setup
+                .usage
+                .iter()
+                .map(|flag| format!("wgpu::BufferUsages::{}", flag))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            setup.pipeline_label,
+            setup.entry_point
+        );
+
+        let set_bind_group = match &setup.binding {
+            Some(binding) => {
+                code.push_str(&format!(
+                    "    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {{\n        \
+                        label: Some(\"Bind Group\"),\n        \
+                        layout: &compute_pipeline.get_bind_group_layout({}),\n        \
+                        entries: &[wgpu::BindGroupEntry {{\n            \
+                            binding: {},\n            \
+                            resource: buffer.as_entire_binding(),\n        \
+                        }}],\n    \
+                    }});\n\n",
+                    binding.group, binding.binding
+                ));
+                format!(
+                    "        compute_pass.set_bind_group({}, &bind_group, &[]);\n",
+                    binding.group
+                )
+            }
+            None => {
+                code.push_str(
+                    "    // The entry point uses no storage buffer, so the buffer is only\n    \
+                    // read back unchanged\n\n",
+                );
+                String::new()
+            }
+        };
+
+        code.push_str(&format!(
+            "    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {{\n        \
+                label: Some(\"Compute Encoder\"),\n    \
+            }});\n    \
+            {{\n        \
+                let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {{\n            \
+                    label: Some(\"Compute Pass\"),\n            \
+                    timestamp_writes: None,\n        \
+                }});\n        \
+                compute_pass.set_pipeline(&compute_pipeline);\n\
+            {}        \
+                compute_pass.dispatch_workgroups(WORKGROUPS[0], WORKGROUPS[1], WORKGROUPS[2]);\n    \
+            }}\n    \
+            encoder.copy_buffer_to_buffer(&buffer, 0, &readback_buffer, 0, BUFFER_SIZE);\n    \
+            queue.submit(std::iter::once(encoder.finish()));\n\
+            \n    \
+            let slice = readback_buffer.slice(..);\n    \
+            slice.map_async(wgpu::MapMode::Read, |result| {{\n        \
+                result.expect(\"Failed to map the readback buffer\")\n    \
+            }});\n    \
+            device.poll(wgpu::PollType::wait_indefinitely()).unwrap();\n    \
+            let output: Vec<{}> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();\n    \
+            readback_buffer.unmap();\n\
+            \n    \
+            println!(\n        \
+                \"dispatch_workgroups({{}}, {{}}, {{}})\",\n        \
+                WORKGROUPS[0], WORKGROUPS[1], WORKGROUPS[2]\n    \
+            );\n    \
+            println!(\"{} ({{}} values):\", output.len());\n    \
+            for (index, value) in output.iter().take(PRINTED_VALUES).enumerate() {{\n        \
+                println!(\"  [{{}}] {{}}\", index, value);\n    \
+            }}\n    \
+            if output.len() > PRINTED_VALUES {{\n        \
+                println!(\"  ... {{}} more\", output.len() - PRINTED_VALUES);\n    \
+            }}\n\
+            }}\n",
+            set_bind_group, element, setup.buffer_label
+        ));
+
+        Ok(code)
+    }
+
+    /// Generate shader file
+    fn generate_shader_file(
+        &self,
+        output_dir: &Path,
+        shader_source: &str,
+    ) -> Result<(), std::io::Error> {
+        let shaders_dir = output_dir.join("shaders");
+        std::fs::create_dir_all(&shaders_dir)?;
+        std::fs::write(shaders_dir.join("shader.wgsl"), shader_source)
+    }
+
+    /// Generate README.md file
+    fn generate_readme(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        if self.config.example_type == ExampleType::Compute {
+            return self.generate_compute_readme(output_dir);
+        }
+        let web_section = match self.config.target {
+            ExportTarget::Native | ExportTarget::Cpp | ExportTarget::Python => "",
+            ExportTarget::Web => {
+                "## Running in the Browser\n\
+                \n\
+                Install [wasm-pack](https://rustwasm.github.io/wasm-pack/installer/), build the\n\
+                WebAssembly module into `pkg/` and serve this directory over HTTP:\n\
+                \n\
+                ```bash\n\
+                ./build.sh\n\
+                python3 -m http.server 8000\n\
+                ```\n\
+                \n\
+                Then open http://localhost:8000 in a browser with WebGPU support\n\
+                (Chrome or Edge 113+). `index.html` renders into its `<canvas id=\"canvas\">`.\n\
+                \n"
+            }
+        };
+        let content = format!(
+            "# {}\n\
+            \n\
+            Standalone WebGPU project generated from wgpu_playground.\n\
+            \n\
+            ## Building\n\
+            \n\
+            ```bash\n\
+            cargo build --release\n\
+            ```\n\
+            \n\
+            ## Running\n\
+            \n\
+            ```bash\n\
+            cargo run --release\n\
+            ```\n\
+            \n\
+            {}\
+            ## Requirements\n\
+            \n\
+            - Rust (latest stable version)\n\
+            - A GPU with WebGPU support (Vulkan, Metal, or DirectX 12)\n\
+            \n\
+            ## Note\n\
+            \n\
+            This code uses winit 0.30. If you encounter compilation errors,\n\
+            you may need to update the event loop code to use ApplicationHandler.\n\
+            See the winit 0.30 migration guide for details.\n\
+            \n\
+            ## Generated Configuration\n\
+            \n\
+            - Canvas size: {}x{}\n\
+            - Clear color: RGB({:.2}, {:.2}, {:.2})\n\
+            - Example type: {:?}\n\
+            \n\
+            ---\n\
+            \n\
+            Generated by wgpu_playground\n",
+            self.config.project_name,
+            web_section,
+            self.config.canvas_width,
+            self.config.canvas_height,
+            self.config.clear_color[0],
+            self.config.clear_color[1],
+            self.config.clear_color[2],
+            self.config.example_type
+        );
+
+        std::fs::write(output_dir.join("README.md"), content)
+    }
+
+    /// Generate README.md file of a compute project
+    fn generate_compute_readme(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        let setup = Self::compute_setup(&self.config)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let binding = match &setup.binding {
+            Some(b) => format!("@group({}) @binding({})", b.group, b.binding),
+            None => "not used by the shader".to_string(),
+        };
+        let content = format!(
+            "# {}\n\
+            \n\
+            Standalone WebGPU compute project generated from wgpu_playground.\n\
+            \n\
+            It fills a storage buffer with 0, 1, 2, ..., runs the compute shader\n\
+            on it and prints the first values of the result. No window is opened.\n\
+            \n\
+            ## Running\n\
+            \n\
+            ```bash\n\
+            cargo run --release\n\
+            ```\n\
+            \n\
+            ## Requirements\n\
+            \n\
+            - Rust (latest stable version)\n\
+            - A GPU with WebGPU support (Vulkan, Metal, or DirectX 12)\n\
+            \n\
+            ## Generated Configuration\n\
+            \n\
+            - Entry point: {}\n\
+            - Buffer: \"{}\", {} bytes, {}\n\
+            - Dispatch: {}x{}x{} workgroups\n\
+            - Example type: {:?}\n\
+            \n\
+            ---\n\
+            \n\
+            Generated by wgpu_playground\n",
+            self.config.project_name,
+            setup.entry_point,
+            setup.buffer_label,
+            setup.buffer_size,
+            binding,
+            setup.workgroups[0],
+            setup.workgroups[1],
+            setup.workgroups[2],
+            self.config.example_type
+        );
+
+        std::fs::write(output_dir.join("README.md"), content)
+    }
+
+    /// Generate main.rs from playground state
+    fn generate_playground_main(&self, state: &PlaygroundState) -> String {
+        let mut code = String::new();
+
+        // Add imports
+        code.push_str(
+            "use std::sync::Arc;\n\
+            use winit::{\n    \
+                event::*,\n    \
+                event_loop::EventLoop,\n    \
+                window::Window,\n\
+            };\n\
+            \n",
+        );
+
+        // Add shader source if available
+        if let Some(ref shader_editor) = state.shader_editor {
+            code.push_str(&format!(
+                "const SHADER_SOURCE: &str = r#\"{}\"#;\n\n",
+                shader_editor.source_code
+            ));
+        }
+
+        // Add State struct
+        code.push_str(&self.generate_state_struct(state));
+
+        // Add State implementation
+        code.push_str(&self.generate_state_impl(state));
+
+        // Depth and multisampled color targets follow the surface size
+        if Self::needs_attachments(state) {
+            code.push_str(ATTACHMENT_HELPER);
+        }
+
+        // Add main function
+        code.push_str(&self.generate_main_function());
+
+        code
+    }
+
+    /// The playground state to export, or a render pipeline drawing three
+    /// vertices with the configured shader, the default triangle without one
+    fn state_or_triangle(config: &CodeGenConfig) -> Cow<'_, PlaygroundState> {
+        match &config.playground_state {
+            Some(state) => Cow::Borrowed(state),
+            None => Cow::Owned(Self::triangle_state(config)),
+        }
+    }
+
+    /// Render pipeline drawing the configured shader, or the default triangle
+    fn triangle_state(config: &CodeGenConfig) -> PlaygroundState {
+        PlaygroundState {
+            shader_editor: Some(ShaderEditorState {
+                source_code: config
+                    .shader_source
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_TRIANGLE_SHADER.to_string()),
+                label: "Shader".to_string(),
+                ..Default::default()
+            }),
+            render_pipeline_panel: Some(RenderPipelinePanelState {
+                label: "Render Pipeline".to_string(),
+                vertex_entry_point: "vs_main".to_string(),
+                fragment_entry_point: "fs_main".to_string(),
+                topology: "TriangleList".to_string(),
+                cull_mode: "None".to_string(),
+                front_face: "Ccw".to_string(),
+                polygon_mode: "Fill".to_string(),
+                sample_count: 1,
+                write_red: true,
+                write_green: true,
+                write_blue: true,
+                write_alpha: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Render pipeline of the export; it needs the shader module
+    fn render_pipeline_state(state: &PlaygroundState) -> Option<&RenderPipelinePanelState> {
+        state
+            .render_pipeline_panel
+            .as_ref()
+            .filter(|_| state.shader_editor.is_some())
+    }
+
+    /// Compute pipeline of the export; it needs the shader module
+    fn compute_pipeline_state(state: &PlaygroundState) -> Option<&ComputePipelinePanelState> {
+        state
+            .compute_pipeline_panel
+            .as_ref()
+            .filter(|_| state.shader_editor.is_some())
+    }
+
+    /// Whether the render pipeline draws into a depth or multisampled target
+    fn needs_attachments(state: &PlaygroundState) -> bool {
+        Self::render_pipeline_state(state)
+            .is_some_and(|pipeline| pipeline.enable_depth_stencil || pipeline.sample_count > 1)
+    }
+
+    /// Entries of the exported bind group, from the bind group panel or else
+    /// the bind group layout panel
+    fn bind_group_entries(state: &PlaygroundState) -> Vec<&BindGroupEntryState> {
+        let entries = match &state.bind_group_panel {
+            Some(panel) if !panel.entries.is_empty() => &panel.entries,
+            _ => match &state.bind_group_layout_panel {
+                Some(panel) => &panel.entries,
+                None => return Vec::new(),
+            },
+        };
+        entries
+            .iter()
+            .filter(|entry| {
+                let known = KNOWN_BINDING_TYPES.contains(&entry.binding_type.as_str());
+                if !known {
+                    log::warn!(
+                        "Skipping binding {} of unknown type '{}'",
+                        entry.binding,
+                        entry.binding_type
+                    );
+                }
+                known
+            })
+            .collect()
+    }
+
+    /// The loaded image of the texture panel decoded to RGBA8, if the
+    /// texture is a 2D RGBA8 texture it can be uploaded to
+    fn texture_image(texture_state: &TexturePanelState) -> Option<image::RgbaImage> {
+        let encoded = texture_state.image_data.as_ref()?;
+        if !matches!(
+            texture_state.format.as_str(),
+            "Rgba8Unorm" | "Rgba8UnormSrgb"
+        ) || !matches!(texture_state.dimension.as_str(), "D2" | "")
+        {
+            log::warn!(
+                "Not embedding the image of texture '{}': only 2D Rgba8Unorm and \
+                 Rgba8UnormSrgb textures are uploaded",
+                texture_state.label
+            );
+            return None;
+        }
+        BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| image::load_from_memory(&bytes).map_err(|e| e.to_string()))
+            .inspect_err(|e| {
+                log::warn!(
+                    "Failed to decode the image of texture '{}': {}",
+                    texture_state.label,
+                    e
+                )
+            })
+            .ok()
+            .map(|image| image.to_rgba8())
+    }
+
+    /// Write the texture image included by the generated code, if any
+    fn generate_texture_data(
+        &self,
+        output_dir: &Path,
+        state: &PlaygroundState,
+    ) -> Result<(), std::io::Error> {
+        let Some(image) = state.texture_panel.as_ref().and_then(Self::texture_image) else {
+            return Ok(());
+        };
+        let src_dir = output_dir.join("src");
+        std::fs::create_dir_all(&src_dir)?;
+        std::fs::write(src_dir.join(TEXTURE_DATA_FILE), image.as_raw())
+    }
+
+    /// `wgpu::Features` expression for the features the configuration needs
+    fn required_features(state: &PlaygroundState) -> String {
+        let mut features = Vec::new();
+        if let Some(pipeline) = Self::render_pipeline_state(state) {
+            if pipeline.enable_depth_stencil && pipeline.depth_format == "Depth32FloatStencil8" {
+                features.push("wgpu::Features::DEPTH32FLOAT_STENCIL8");
+            }
+            match pipeline.polygon_mode.as_str() {
+                "Line" => features.push("wgpu::Features::POLYGON_MODE_LINE"),
+                "Point" => features.push("wgpu::Features::POLYGON_MODE_POINT"),
+                _ => {}
+            }
+        }
+        if let Some(texture) = &state.texture_panel {
+            if texture.format.starts_with("Bc") {
+                features.push("wgpu::Features::TEXTURE_COMPRESSION_BC");
+            }
+        }
+        if features.is_empty() {
+            "wgpu::Features::empty()".to_string()
+        } else {
+            features.join(" | ")
+        }
+    }
+
+    /// Generate State struct based on playground configuration
+    fn generate_state_struct(&self, state: &PlaygroundState) -> String {
+        let mut code = String::from("struct State {\n");
+        code.push_str("    surface: wgpu::Surface<'static>,\n");
+        code.push_str("    device: wgpu::Device,\n");
+        code.push_str("    queue: wgpu::Queue,\n");
+        code.push_str("    config: wgpu::SurfaceConfiguration,\n");
+        code.push_str("    size: winit::dpi::PhysicalSize<u32>,\n");
+        code.push_str("    window: Arc<Window>,\n");
+
+        // Resources are kept alive for the whole run, even those only the
+        // bind group refers to
+        if state.buffer_panel.is_some() {
+            code.push_str("    #[allow(dead_code)]\n");
+            code.push_str("    buffer: wgpu::Buffer,\n");
+        }
+
+        if state.texture_panel.is_some() {
+            code.push_str("    #[allow(dead_code)]\n");
+            code.push_str("    texture: wgpu::Texture,\n");
+            code.push_str("    #[allow(dead_code)]\n");
+            code.push_str("    texture_view: wgpu::TextureView,\n");
+        }
+
+        if state.sampler_panel.is_some() {
+            code.push_str("    #[allow(dead_code)]\n");
+            code.push_str("    sampler: wgpu::Sampler,\n");
+        }
+
+        if state.shader_editor.is_some() {
+            code.push_str("    #[allow(dead_code)]\n");
+            code.push_str("    shader_module: wgpu::ShaderModule,\n");
+        }
+
+        if !Self::bind_group_entries(state).is_empty() {
+            code.push_str("    bind_group: wgpu::BindGroup,\n");
+        }
+
+        if let Some(pipeline) = Self::render_pipeline_state(state) {
+            code.push_str("    render_pipeline: wgpu::RenderPipeline,\n");
+            if pipeline.enable_depth_stencil {
+                code.push_str("    depth_view: wgpu::TextureView,\n");
+            }
+            if pipeline.sample_count > 1 {
+                code.push_str("    msaa_view: wgpu::TextureView,\n");
+            }
+        }
+
+        if Self::compute_pipeline_state(state).is_some() {
+            code.push_str("    compute_pipeline: wgpu::ComputePipeline,\n");
+        }
+
+        code.push_str("}\n\n");
+        code
+    }
+
+    /// Generate State implementation
+    fn generate_state_impl(&self, state: &PlaygroundState) -> String {
+        let mut code =
+            String::from("impl State {\n    async fn new(window: Arc<Window>) -> Self {\n");
+
+        // Basic setup
+        code.push_str(&format!(
+            "        let size = window.inner_size();\n\
+            \n        \
+            let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {{\n            \
+                backends: wgpu::Backends::all(),\n            \
+                ..wgpu::InstanceDescriptor::new_without_display_handle()\n        \
+            }});\n\
+            \n        \
+            let surface = instance.create_surface(window.clone()).unwrap();\n\
+            \n        \
+            let adapter = instance\n            \
+                .request_adapter(&wgpu::RequestAdapterOptions {{\n                \
+                    power_preference: wgpu::PowerPreference::default(),\n                \
+                    compatible_surface: Some(&surface),\n                \
+                    force_fallback_adapter: false,\n            \
+                }})\n            \
+                .await\n            \
+                .unwrap();\n\
+            \n        \
+            let (device, queue) = adapter\n            \
+                .request_device(&wgpu::DeviceDescriptor {{\n                \
+                    required_features: {},\n                \
+                    required_limits: wgpu::Limits::default(),\n                \
+                    label: Some(\"Device\"),\n                \
+                    memory_hints: Default::default(),\n                \
+                    experimental_features: Default::default(),\n                \
+                    trace: wgpu::Trace::Off,\n            \
+                }})\n            \
+                .await\n            \
+                .unwrap();\n\
+            \n        \
+            let surface_caps = surface.get_capabilities(&adapter);\n        \
+            let surface_format = surface_caps\n            \
+                .formats\n            \
+                .iter()\n            \
+                .find(|f| f.is_srgb())\n            \
+                .copied()\n            \
+                .unwrap_or(surface_caps.formats[0]);\n\
+            \n        \
+            let config = wgpu::SurfaceConfiguration {{\n            \
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,\n            \
+                format: surface_format,\n            \
+                width: size.width.max(1),\n            \
+                height: size.height.max(1),\n            \
+                present_mode: wgpu::PresentMode::Fifo,\n            \
+                alpha_mode: surface_caps.alpha_modes[0],\n            \
+                view_formats: vec![],\n            \
+                desired_maximum_frame_latency: 2,\n        \
+            }};\n        \
+            surface.configure(&device, &config);\n\n",
+            Self::required_features(state)
+        ));
+
+        // Add buffer creation if configured
+        if let Some(ref buffer_state) = state.buffer_panel {
+            code.push_str(&self.generate_buffer_creation(buffer_state));
+        }
+
+        // Add texture creation if configured
+        if let Some(ref texture_state) = state.texture_panel {
+            code.push_str(&self.generate_texture_creation(texture_state));
+        }
+
+        // Add sampler creation if configured
+        if let Some(ref sampler_state) = state.sampler_panel {
+            code.push_str(&self.generate_sampler_creation(sampler_state));
+        }
+
+        // Add shader module creation if configured
+        if let Some(ref shader_state) = state.shader_editor {
+            code.push_str(&self.generate_shader_module_creation(shader_state));
+        }
+
+        // Add the bind group and its layout if configured
+        let has_bind_group = !Self::bind_group_entries(state).is_empty();
+        if has_bind_group {
+            code.push_str(&self.generate_bind_group_creation(state));
+        }
+
+        // Both pipelines share a layout with the bind group at index 0
+        let render_pipeline = Self::render_pipeline_state(state);
+        let compute_pipeline = Self::compute_pipeline_state(state);
+        if render_pipeline.is_some() || compute_pipeline.is_some() {
+            code.push_str(&format!(
+                "        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {{\n            \
+                    label: Some(\"Pipeline Layout\"),\n            \
+                    bind_group_layouts: &[{}],\n            \
+                    immediate_size: 0,\n        \
+                }});\n\n",
+                if has_bind_group {
+                    "Some(&bind_group_layout)"
+                } else {
+                    ""
+                }
+            ));
+        }
+
+        // Add render pipeline creation if configured
+        if let Some(pipeline_state) = render_pipeline {
+            code.push_str(&self.generate_render_pipeline_creation(pipeline_state));
+        }
+
+        // Add compute pipeline creation if configured
+        if let Some(compute_state) = compute_pipeline {
+            code.push_str(&self.generate_compute_pipeline_creation(compute_state));
+        }
+
+        // Return State instance
+        code.push_str("        Self {\n");
+        code.push_str("            window,\n");
+        code.push_str("            surface,\n");
+        code.push_str("            device,\n");
+        code.push_str("            queue,\n");
+        code.push_str("            config,\n");
+        code.push_str("            size,\n");
+
+        if state.buffer_panel.is_some() {
+            code.push_str("            buffer,\n");
+        }
+        if state.texture_panel.is_some() {
+            code.push_str("            texture,\n");
+            code.push_str("            texture_view,\n");
+        }
+        if state.sampler_panel.is_some() {
+            code.push_str("            sampler,\n");
+        }
+        if state.shader_editor.is_some() {
+            code.push_str("            shader_module,\n");
+        }
+        if has_bind_group {
+            code.push_str("            bind_group,\n");
+        }
+        if let Some(pipeline) = render_pipeline {
+            code.push_str("            render_pipeline,\n");
+            if pipeline.enable_depth_stencil {
+                code.push_str("            depth_view,\n");
+            }
+            if pipeline.sample_count > 1 {
+                code.push_str("            msaa_view,\n");
+            }
+        }
+        if compute_pipeline.is_some() {
+            code.push_str("            compute_pipeline,\n");
+        }
+
+        code.push_str("        }\n");
+        code.push_str("    }\n");
+
+        // Add resize method, which also resizes the pipeline's attachments
+        code.push_str(
+            "\n    fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {\n        \
+                if new_size.width > 0 && new_size.height > 0 {\n            \
+                    self.size = new_size;\n            \
+                    self.config.width = new_size.width;\n            \
+                    self.config.height = new_size.height;\n            \
+                    self.surface.configure(&self.device, &self.config);\n",
+        );
+        if let Some(pipeline) = render_pipeline {
+            if pipeline.enable_depth_stencil {
+                code.push_str(&format!(
+                    "            self.depth_view = create_attachment(\n                \
+                        &self.device,\n                \
+                        &self.config,\n                \
+                        wgpu::TextureFormat::{},\n                \
+                        {},\n                \
+                        \"Depth Texture\",\n            \
+                    );\n",
+                    variant(&pipeline.depth_format, "Depth24Plus"),
+                    pipeline.sample_count.max(1)
+                ));
+            }
+            if pipeline.sample_count > 1 {
+                code.push_str(&format!(
+                    "            self.msaa_view = create_attachment(\n                \
+                        &self.device,\n                \
+                        &self.config,\n                \
+                        self.config.format,\n                \
+                        {},\n                \
+                        \"Multisampled Color Texture\",\n            \
+                    );\n",
+                    pipeline.sample_count
+                ));
+            }
+        }
+        code.push_str("        }\n    }\n");
+
+        // Add render method
+        code.push_str(&self.generate_render_method(state));
+
+        code.push_str("}\n\n");
+        code
+    }
+
+    /// Generate buffer creation code
+    fn generate_buffer_creation(&self, buffer_state: &BufferPanelState) -> String {
+        let mut size = parse_or(&buffer_state.size, DEFAULT_BUFFER_SIZE, "buffer size");
+        // Buffers mapped at creation need a size aligned to 4 bytes
+        if buffer_state.mapped_at_creation {
+            size = size.next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+        }
+
+        let mut usage_flags = Vec::new();
+        for (enabled, flag) in [
+            (buffer_state.usage_vertex, "wgpu::BufferUsages::VERTEX"),
+            (buffer_state.usage_index, "wgpu::BufferUsages::INDEX"),
+            (buffer_state.usage_uniform, "wgpu::BufferUsages::UNIFORM"),
+            (buffer_state.usage_storage, "wgpu::BufferUsages::STORAGE"),
+            (buffer_state.usage_indirect, "wgpu::BufferUsages::INDIRECT"),
+            (buffer_state.usage_copy_src, "wgpu::BufferUsages::COPY_SRC"),
+            (buffer_state.usage_copy_dst, "wgpu::BufferUsages::COPY_DST"),
+            (buffer_state.usage_map_read, "wgpu::BufferUsages::MAP_READ"),
+            (
+                buffer_state.usage_map_write,
+                "wgpu::BufferUsages::MAP_WRITE",
+            ),
+            (
+                buffer_state.usage_query_resolve,
+                "wgpu::BufferUsages::QUERY_RESOLVE",
+            ),
+        ] {
+            if enabled {
+                usage_flags.push(flag);
+            }
+        }
+
+        let usage = if usage_flags.is_empty() {
+            "wgpu::BufferUsages::VERTEX".to_string()
+        } else {
+            usage_flags.join(" | ")
+        };
+
+        let mut code = format!(
+            "        let buffer = device.create_buffer(&wgpu::BufferDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                size: {},\n            \
+                usage: {},\n            \
+                mapped_at_creation: {},\n        \
+            }});\n",
+            buffer_state.label, size, usage, buffer_state.mapped_at_creation
+        );
+        if buffer_state.mapped_at_creation {
+            code.push_str("        buffer.unmap();\n");
+        }
+        code.push('\n');
+        code
+    }
+
+    /// Generate texture creation code, uploading the loaded image if any
+    fn generate_texture_creation(&self, texture_state: &TexturePanelState) -> String {
+        let image = Self::texture_image(texture_state);
+        let (width, height, depth) = match &image {
+            Some(image) => (image.width(), image.height(), 1),
+            None => (
+                parse_or(&texture_state.width, DEFAULT_TEXTURE_WIDTH, "texture width"),
+                parse_or(
+                    &texture_state.height,
+                    DEFAULT_TEXTURE_HEIGHT,
+                    "texture height",
+                ),
+                parse_or(&texture_state.depth, DEFAULT_TEXTURE_DEPTH, "texture depth"),
+            ),
+        };
+        let mip_levels = parse_or(&texture_state.mip_levels, 1, "texture mip level count");
+        let sample_count = parse_or(&texture_state.sample_count, 1, "texture sample count");
+
+        let mut usage_flags = Vec::new();
+        if texture_state.usage_copy_src {
+            usage_flags.push("wgpu::TextureUsages::COPY_SRC");
+        }
+        // The image is written to the texture
+        if texture_state.usage_copy_dst || image.is_some() {
+            usage_flags.push("wgpu::TextureUsages::COPY_DST");
+        }
+        if texture_state.usage_texture_binding {
+            usage_flags.push("wgpu::TextureUsages::TEXTURE_BINDING");
+        }
+        if texture_state.usage_storage_binding {
+            usage_flags.push("wgpu::TextureUsages::STORAGE_BINDING");
+        }
+        if texture_state.usage_render_attachment {
+            usage_flags.push("wgpu::TextureUsages::RENDER_ATTACHMENT");
+        }
+
+        let usage = if usage_flags.is_empty() {
+            "wgpu::TextureUsages::TEXTURE_BINDING".to_string()
+        } else {
+            usage_flags.join(" | ")
+        };
+
+        let mut code = format!(
+            "        let texture = device.create_texture(&wgpu::TextureDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                size: wgpu::Extent3d {{\n                \
+                    width: {},\n                \
+                    height: {},\n                \
+                    depth_or_array_layers: {},\n            \
+                }},\n            \
+                mip_level_count: {},\n            \
+                sample_count: {},\n            \
+                dimension: wgpu::TextureDimension::{},\n            \
+                format: wgpu::TextureFormat::{},\n            \
+                usage: {},\n            \
+                view_formats: &[],\n        \
+            }});\n",
+            texture_state.label,
+            width,
+            height,
+            depth,
+            mip_levels,
+            sample_count,
+            variant(&texture_state.dimension, "D2"),
+            variant(&texture_state.format, "Rgba8UnormSrgb"),
+            usage
+        );
+        if image.is_some() {
+            code.push_str(&format!(
+                "        // The image loaded in the playground, as RGBA8 pixels\n        \
+                queue.write_texture(\n            \
+                    wgpu::TexelCopyTextureInfo {{\n                \
+                        texture: &texture,\n                \
+                        mip_level: 0,\n                \
+                        origin: wgpu::Origin3d::ZERO,\n                \
+                        aspect: wgpu::TextureAspect::All,\n            \
+                    }},\n            \
+                    include_bytes!(\"{}\"),\n            \
+                    wgpu::TexelCopyBufferLayout {{\n                \
+                        offset: 0,\n                \
+                        bytes_per_row: Some({}),\n                \
+                        rows_per_image: Some({}),\n            \
+                    }},\n            \
+                    wgpu::Extent3d {{\n                \
+                        width: {},\n                \
+                        height: {},\n                \
+                        depth_or_array_layers: 1,\n            \
+                    }},\n        \
+                );\n",
+                TEXTURE_DATA_FILE,
+                width * 4,
+                height,
+                width,
+                height
+            ));
+        }
+        code.push_str(
+            "\n        \
+            let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());\n\n",
+        );
+        code
+    }
+
+    /// Generate sampler creation code
+    fn generate_sampler_creation(&self, sampler_state: &SamplerPanelState) -> String {
+        let lod_min_clamp = parse_or(&sampler_state.lod_min_clamp, 0.0f32, "LOD min clamp");
+        let lod_max_clamp = parse_or(&sampler_state.lod_max_clamp, 32.0f32, "LOD max clamp");
+        let anisotropy = parse_or(&sampler_state.max_anisotropy, 1u16, "anisotropy").clamp(1, 16);
+        let compare = match &sampler_state.compare {
+            Some(compare) => format!(
+                "Some(wgpu::CompareFunction::{})",
+                variant(compare, "LessEqual")
+            ),
+            None => "None".to_string(),
+        };
+
+        format!(
+            "        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                address_mode_u: wgpu::AddressMode::{},\n            \
+                address_mode_v: wgpu::AddressMode::{},\n            \
+                address_mode_w: wgpu::AddressMode::{},\n            \
+                mag_filter: wgpu::FilterMode::{},\n            \
+                min_filter: wgpu::FilterMode::{},\n            \
+                mipmap_filter: wgpu::MipmapFilterMode::{},\n            \
+                lod_min_clamp: {:?},\n            \
+                lod_max_clamp: {:?},\n            \
+                compare: {},\n            \
+                anisotropy_clamp: {},\n            \
+                border_color: None,\n        \
+            }});\n\n",
+            sampler_state.label,
+            variant(&sampler_state.address_mode_u, "ClampToEdge"),
+            variant(&sampler_state.address_mode_v, "ClampToEdge"),
+            variant(&sampler_state.address_mode_w, "ClampToEdge"),
+            variant(&sampler_state.mag_filter, "Linear"),
+            variant(&sampler_state.min_filter, "Linear"),
+            variant(&sampler_state.mipmap_filter, "Linear"),
+            lod_min_clamp,
+            lod_max_clamp,
+            compare,
+            anisotropy
+        )
+    }
+
+    /// Generate shader module creation code
+    fn generate_shader_module_creation(&self, shader_state: &ShaderEditorState) -> String {
+        format!(
+            "        let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                source: wgpu::ShaderSource::Wgsl(SHADER_SOURCE.into()),\n        \
+            }});\n\n",
+            shader_state.label
+        )
+    }
+
+    /// Generate the bind group layout and the bind group
+    ///
+    /// Entries are bound to the configured buffer, texture and sampler when
+    /// those fit the binding type; the others get small placeholder resources
+    /// so the bind group is always valid.
+    fn generate_bind_group_creation(&self, state: &PlaygroundState) -> String {
+        let entries = Self::bind_group_entries(state);
+        let label = state
+            .bind_group_panel
+            .as_ref()
+            .filter(|panel| !panel.entries.is_empty())
+            .map(|panel| panel.label.as_str())
+            .or_else(|| {
+                state
+                    .bind_group_layout_panel
+                    .as_ref()
+                    .map(|p| p.label.as_str())
+            })
+            .filter(|label| !label.is_empty())
+            .unwrap_or("Bind Group");
+
+        let mut layout_entries = String::new();
+        let mut placeholders = String::new();
+        let mut group_entries = String::new();
+        for entry in entries {
+            let mut stages = Vec::new();
+            if entry.visibility_vertex {
+                stages.push("wgpu::ShaderStages::VERTEX");
+            }
+            if entry.visibility_fragment {
+                stages.push("wgpu::ShaderStages::FRAGMENT");
+            }
+            if entry.visibility_compute {
+                stages.push("wgpu::ShaderStages::COMPUTE");
+            }
+            let visibility = if stages.is_empty() {
+                "wgpu::ShaderStages::NONE".to_string()
+            } else {
+                stages.join(" | ")
+            };
+
+            let (ty, resource) = self.bind_group_resource(state, entry, &mut placeholders);
+            layout_entries.push_str(&format!(
+                "                wgpu::BindGroupLayoutEntry {{\n                    \
+                    binding: {},\n                    \
+                    visibility: {},\n                    \
+                    ty: {},\n                    \
+                    count: None,\n                \
+                }},\n",
+                entry.binding, visibility, ty
+            ));
+            group_entries.push_str(&format!(
+                "                wgpu::BindGroupEntry {{\n                    \
+                    binding: {},\n                    \
+                    resource: {},\n                \
+                }},\n",
+                entry.binding, resource
+            ));
+        }
+
+        format!(
+            "        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                entries: &[\n\
+            {}            \
+                ],\n        \
+            }});\n\
+            \n\
+            {}        \
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                layout: &bind_group_layout,\n            \
+                entries: &[\n\
+            {}            \
+                ],\n        \
+            }});\n\n",
+            format!("{} Layout", label),
+            layout_entries,
+            placeholders,
+            label,
+            group_entries
+        )
+    }
+
+    /// `wgpu::BindingType` and `wgpu::BindingResource` expressions of a bind
+    /// group entry, adding the creation of a placeholder resource to
+    /// `placeholders` when no configured resource fits
+    fn bind_group_resource(
+        &self,
+        state: &PlaygroundState,
+        entry: &BindGroupEntryState,
+        placeholders: &mut String,
+    ) -> (String, String) {
+        let binding = entry.binding;
+        let placeholder_buffer = |placeholders: &mut String, usage: &str| {
+            placeholders.push_str(&format!(
+                "        // No configured buffer fits binding {binding}\n        \
+                let binding_{binding}_buffer = device.create_buffer(&wgpu::BufferDescriptor {{\n            \
+                    label: Some(\"Binding {binding} Buffer\"),\n            \
+                    size: {DEFAULT_BUFFER_SIZE},\n            \
+                    usage: {usage} | wgpu::BufferUsages::COPY_DST,\n            \
+                    mapped_at_creation: false,\n        \
+                }});\n\n"
+            ));
+            format!("binding_{binding}_buffer.as_entire_binding()")
+        };
+        let placeholder_texture = |placeholders: &mut String, format: &str, usage: &str| {
+            placeholders.push_str(&format!(
+                "        // No configured texture fits binding {binding}\n        \
+                let binding_{binding}_view = device\n            \
+                    .create_texture(&wgpu::TextureDescriptor {{\n                \
+                        label: Some(\"Binding {binding} Texture\"),\n                \
+                        size: wgpu::Extent3d {{\n                    \
+                            width: {DEFAULT_TEXTURE_WIDTH},\n                    \
+                            height: {DEFAULT_TEXTURE_HEIGHT},\n                    \
+                            depth_or_array_layers: 1,\n                \
+                        }},\n                \
+                        mip_level_count: 1,\n                \
+                        sample_count: 1,\n                \
+                        dimension: wgpu::TextureDimension::D2,\n                \
+                        format: wgpu::TextureFormat::{format},\n                \
+                        usage: {usage},\n                \
+                        view_formats: &[],\n            \
+                    }})\n            \
+                    .create_view(&wgpu::TextureViewDescriptor::default());\n\n"
+            ));
+            format!("wgpu::BindingResource::TextureView(&binding_{binding}_view)")
+        };
+
+        let fits = Self::configured_resource_fits(state, entry);
+        match entry.binding_type.as_str() {
+            "UniformBuffer" => {
+                let resource = if fits {
+                    "buffer.as_entire_binding()".to_string()
+                } else {
+                    placeholder_buffer(placeholders, "wgpu::BufferUsages::UNIFORM")
+                };
+                (
+                    "wgpu::BindingType::Buffer {\n                        \
+                        ty: wgpu::BufferBindingType::Uniform,\n                        \
+                        has_dynamic_offset: false,\n                        \
+                        min_binding_size: None,\n                    \
+                    }"
+                    .to_string(),
+                    resource,
+                )
+            }
+            "StorageBuffer" => {
+                let resource = if fits {
+                    "buffer.as_entire_binding()".to_string()
+                } else {
+                    placeholder_buffer(placeholders, "wgpu::BufferUsages::STORAGE")
+                };
+                (
+                    format!(
+                        "wgpu::BindingType::Buffer {{\n                        \
+                            ty: wgpu::BufferBindingType::Storage {{ read_only: {} }},\n                        \
+                            has_dynamic_offset: false,\n                        \
+                            min_binding_size: None,\n                    \
+                        }}",
+                        entry.read_only
+                    ),
+                    resource,
+                )
+            }
+            "Texture" => {
+                let resource = if fits {
+                    "wgpu::BindingResource::TextureView(&texture_view)".to_string()
+                } else {
+                    placeholder_texture(
+                        placeholders,
+                        "Rgba8Unorm",
+                        "wgpu::TextureUsages::TEXTURE_BINDING",
+                    )
+                };
+                (
+                    "wgpu::BindingType::Texture {\n                        \
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },\n                        \
+                        view_dimension: wgpu::TextureViewDimension::D2,\n                        \
+                        multisampled: false,\n                    \
+                    }"
+                    .to_string(),
+                    resource,
+                )
+            }
+            "Sampler" => {
+                let resource = if fits {
+                    "wgpu::BindingResource::Sampler(&sampler)".to_string()
+                } else {
+                    placeholders.push_str(&format!(
+                        "        // No configured sampler fits binding {binding}\n        \
+                        let binding_{binding}_sampler = device.create_sampler(&wgpu::SamplerDescriptor {{\n            \
+                            label: Some(\"Binding {binding} Sampler\"),\n            \
+                            mag_filter: wgpu::FilterMode::Linear,\n            \
+                            min_filter: wgpu::FilterMode::Linear,\n            \
+                            ..Default::default()\n        \
+                        }});\n\n"
+                    ));
+                    format!("wgpu::BindingResource::Sampler(&binding_{binding}_sampler)")
+                };
+                (
+                    "wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering)".to_string(),
+                    resource,
+                )
+            }
+            _ => {
+                let (access, format) = Self::storage_texture_binding(entry);
+                let resource = if fits {
+                    "wgpu::BindingResource::TextureView(&texture_view)".to_string()
+                } else {
+                    placeholder_texture(
+                        placeholders,
+                        &format,
+                        "wgpu::TextureUsages::STORAGE_BINDING",
+                    )
+                };
+                (
+                    format!(
+                        "wgpu::BindingType::StorageTexture {{\n                        \
+                            access: wgpu::StorageTextureAccess::{:?},\n                        \
+                            format: wgpu::TextureFormat::{},\n                        \
+                            view_dimension: wgpu::TextureViewDimension::D2,\n                    \
+                        }}",
+                        access, format
+                    ),
+                    resource,
+                )
+            }
+        }
+    }
+
+    /// Whether the configured buffer, texture or sampler can be bound to a
+    /// bind group entry, or the entry needs a placeholder resource
+    fn configured_resource_fits(state: &PlaygroundState, entry: &BindGroupEntryState) -> bool {
+        let buffer = state.buffer_panel.as_ref();
+        let texture = state
+            .texture_panel
+            .as_ref()
+            .filter(|texture| texture.sample_count.trim() == "1" || texture.sample_count.is_empty())
+            .filter(|texture| matches!(texture.dimension.as_str(), "D2" | ""));
+        match entry.binding_type.as_str() {
+            "UniformBuffer" => buffer.is_some_and(|buffer| buffer.usage_uniform),
+            "StorageBuffer" => buffer.is_some_and(|buffer| buffer.usage_storage),
+            "Texture" => texture.is_some_and(|texture| {
+                texture.usage_texture_binding && is_filterable_format(&texture.format)
+            }),
+            // The layout declares a filtering sampler, which can't compare
+            "Sampler" => state
+                .sampler_panel
+                .as_ref()
+                .is_some_and(|sampler| sampler.compare.is_none()),
+            _ => {
+                let (_, format) = Self::storage_texture_binding(entry);
+                texture.is_some_and(|texture| {
+                    texture.usage_storage_binding && texture.format == format
+                })
+            }
+        }
+    }
+
+    /// Access and `wgpu::TextureFormat` name of a storage texture entry
+    fn storage_texture_binding(entry: &BindGroupEntryState) -> (StorageTextureAccess, String) {
+        let access = entry
+            .access
+            .as_deref()
+            .and_then(|name| parse_variant(name, &STORAGE_TEXTURE_ACCESSES))
+            .unwrap_or(StorageTextureAccess::WriteOnly);
+        let format = format!("{:?}", BindingTypeConfig::storage_texture_format(access));
+        (access, format)
+    }
+
+    /// Generate render pipeline creation code
+    fn generate_render_pipeline_creation(
+        &self,
+        pipeline_state: &RenderPipelinePanelState,
+    ) -> String {
+        let entry_point = |name: &str| {
+            if name.is_empty() {
+                "None".to_string()
+            } else {
+                format!("Some({:?})", name)
+            }
+        };
+        let cull_mode = match pipeline_state.cull_mode.as_str() {
+            "Front" => "Some(wgpu::Face::Front)",
+            "Back" => "Some(wgpu::Face::Back)",
+            _ => "None",
+        };
+        let blend = if pipeline_state.blend_enabled {
+            format!(
+                "Some(wgpu::BlendState {{\n                        \
+                    color: wgpu::BlendComponent {{\n                            \
+                        src_factor: wgpu::BlendFactor::{},\n                            \
+                        dst_factor: wgpu::BlendFactor::{},\n                            \
+                        operation: wgpu::BlendOperation::{},\n                        \
+                    }},\n                        \
+                    alpha: wgpu::BlendComponent {{\n                            \
+                        src_factor: wgpu::BlendFactor::{},\n                            \
+                        dst_factor: wgpu::BlendFactor::{},\n                            \
+                        operation: wgpu::BlendOperation::{},\n                        \
+                    }},\n                    \
+                }})",
+                variant(&pipeline_state.color_blend_src, "One"),
+                variant(&pipeline_state.color_blend_dst, "Zero"),
+                variant(&pipeline_state.color_blend_op, "Add"),
+                variant(&pipeline_state.alpha_blend_src, "One"),
+                variant(&pipeline_state.alpha_blend_dst, "Zero"),
+                variant(&pipeline_state.alpha_blend_op, "Add")
+            )
+        } else {
+            "Some(wgpu::BlendState::REPLACE)".to_string()
+        };
+        let mut write_mask = Vec::new();
+        for (enabled, flag) in [
+            (pipeline_state.write_red, "wgpu::ColorWrites::RED"),
+            (pipeline_state.write_green, "wgpu::ColorWrites::GREEN"),
+            (pipeline_state.write_blue, "wgpu::ColorWrites::BLUE"),
+            (pipeline_state.write_alpha, "wgpu::ColorWrites::ALPHA"),
+        ] {
+            if enabled {
+                write_mask.push(flag);
+            }
+        }
+        let write_mask = match write_mask.len() {
+            0 => "wgpu::ColorWrites::empty()".to_string(),
+            4 => "wgpu::ColorWrites::ALL".to_string(),
+            _ => write_mask.join(" | "),
+        };
+        let depth_stencil = if pipeline_state.enable_depth_stencil {
+            let mask = |input: &str| {
+                u32::from_str_radix(input.trim_start_matches("0x"), 16).unwrap_or(0xFFFFFFFF)
+            };
+            let face = |compare: &str, fail: &str, depth_fail: &str, pass: &str| {
+                format!(
+                    "wgpu::StencilFaceState {{\n                        \
+                        compare: wgpu::CompareFunction::{},\n                        \
+                        fail_op: wgpu::StencilOperation::{},\n                        \
+                        depth_fail_op: wgpu::StencilOperation::{},\n                        \
+                        pass_op: wgpu::StencilOperation::{},\n                    \
+                    }}",
+                    variant(compare, "Always"),
+                    variant(fail, "Keep"),
+                    variant(depth_fail, "Keep"),
+                    variant(pass, "Keep")
+                )
+            };
+            format!(
+                "Some(wgpu::DepthStencilState {{\n                \
+                    format: wgpu::TextureFormat::{},\n                \
+                    depth_write_enabled: Some({}),\n                \
+                    depth_compare: Some(wgpu::CompareFunction::{}),\n                \
+                    stencil: wgpu::StencilState {{\n                    \
+                        front: {},\n                    \
+                        back: {},\n                    \
+                        read_mask: {:#X},\n                    \
+                        write_mask: {:#X},\n                \
+                    }},\n                \
+                    bias: wgpu::DepthBiasState::default(),\n            \
+                }})",
+                variant(&pipeline_state.depth_format, "Depth24Plus"),
+                pipeline_state.depth_write_enabled,
+                variant(&pipeline_state.depth_compare, "Less"),
+                face(
+                    &pipeline_state.stencil_front_compare,
+                    &pipeline_state.stencil_front_fail_op,
+                    &pipeline_state.stencil_front_depth_fail_op,
+                    &pipeline_state.stencil_front_pass_op
+                ),
+                face(
+                    &pipeline_state.stencil_back_compare,
+                    &pipeline_state.stencil_back_fail_op,
+                    &pipeline_state.stencil_back_depth_fail_op,
+                    &pipeline_state.stencil_back_pass_op
+                ),
+                mask(&pipeline_state.stencil_read_mask),
+                mask(&pipeline_state.stencil_write_mask)
+            )
+        } else {
+            "None".to_string()
+        };
+        let sample_count = pipeline_state.sample_count.max(1);
+
+        let mut code = format!(
+            "        // The color target uses the surface format rather than the\n        \
+            // pipeline panel's target format, so the pipeline can draw to the window\n        \
+            let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                layout: Some(&pipeline_layout),\n            \
+                vertex: wgpu::VertexState {{\n                \
+                    module: &shader_module,\n                \
+                    entry_point: {},\n                \
+                    buffers: &[],\n                \
+                    compilation_options: Default::default(),\n            \
+                }},\n            \
+                fragment: Some(wgpu::FragmentState {{\n                \
+                    module: &shader_module,\n                \
+                    entry_point: {},\n                \
+                    targets: &[Some(wgpu::ColorTargetState {{\n                    \
+                        format: config.format,\n                    \
+                        blend: {},\n                    \
+                        write_mask: {},\n                \
+                    }})],\n                \
+                    compilation_options: Default::default(),\n            \
+                }}),\n            \
+                primitive: wgpu::PrimitiveState {{\n                \
+                    topology: wgpu::PrimitiveTopology::{},\n                \
+                    front_face: wgpu::FrontFace::{},\n                \
+                    cull_mode: {},\n                \
+                    polygon_mode: wgpu::PolygonMode::{},\n                \
+                    ..Default::default()\n            \
+                }},\n            \
+                depth_stencil: {},\n            \
+                multisample: wgpu::MultisampleState {{\n                \
+                    count: {},\n                \
+                    mask: !0,\n                \
+                    alpha_to_coverage_enabled: {},\n            \
+                }},\n            \
+                multiview_mask: None,\n            \
+                cache: None,\n        \
+            }});\n\n",
+            pipeline_state.label,
+            entry_point(&pipeline_state.vertex_entry_point),
+            entry_point(&pipeline_state.fragment_entry_point),
+            blend,
+            write_mask,
+            variant(&pipeline_state.topology, "TriangleList"),
+            variant(&pipeline_state.front_face, "Ccw"),
+            cull_mode,
+            variant(&pipeline_state.polygon_mode, "Fill"),
+            depth_stencil,
+            sample_count,
+            pipeline_state.alpha_to_coverage_enabled && sample_count > 1
+        );
+        if pipeline_state.enable_depth_stencil {
+            code.push_str(&format!(
+                "        let depth_view = create_attachment(\n            \
+                    &device,\n            \
+                    &config,\n            \
+                    wgpu::TextureFormat::{},\n            \
+                    {},\n            \
+                    \"Depth Texture\",\n        \
+                );\n",
+                variant(&pipeline_state.depth_format, "Depth24Plus"),
+                sample_count
+            ));
+        }
+        if sample_count > 1 {
+            code.push_str(&format!(
+                "        let msaa_view = create_attachment(\n            \
+                    &device,\n            \
+                    &config,\n            \
+                    config.format,\n            \
+                    {},\n            \
+                    \"Multisampled Color Texture\",\n        \
+                );\n",
+                sample_count
+            ));
+        }
+        if pipeline_state.enable_depth_stencil || sample_count > 1 {
+            code.push('\n');
+        }
+        code
+    }
+
+    /// Generate compute pipeline creation code
+    fn generate_compute_pipeline_creation(
+        &self,
+        compute_state: &ComputePipelinePanelState,
+    ) -> String {
+        format!(
+            "        let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {{\n            \
+                label: Some({:?}),\n            \
+                layout: Some(&pipeline_layout),\n            \
+                module: &shader_module,\n            \
+                entry_point: {},\n            \
+                compilation_options: Default::default(),\n            \
+                cache: None,\n        \
+            }});\n\n",
+            compute_state.label,
+            if compute_state.entry_point.is_empty() {
+                "None".to_string()
+            } else {
+                format!("Some({:?})", compute_state.entry_point)
+            }
+        )
+    }
+
+    /// Generate render method
+    ///
+    /// Each frame dispatches the compute pipeline, then clears the window and
+    /// draws a full-screen triangle with the render pipeline.
+    fn generate_render_method(&self, state: &PlaygroundState) -> String {
+        let has_bind_group = !Self::bind_group_entries(state).is_empty();
+        let mut code = format!(
+            "\n    fn render(&mut self) {{\n\
+            {}        \
+                let view = output\n            \
+                    .texture\n            \
+                    .create_view(&wgpu::TextureViewDescriptor::default());\n\
+                \n        \
+                let mut encoder = self\n            \
+                    .device\n            \
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {{\n                \
+                        label: Some(\"Render Encoder\"),\n            \
+                    }});\n\n",
+            ACQUIRE_FRAME
+        );
+
+        // Dispatch the compute pipeline before drawing
+        if Self::compute_pipeline_state(state).is_some() {
+            let [x, y, z] = self.config.workgroups.unwrap_or([1, 1, 1]);
+            code.push_str(&format!(
+                "        {{\n            \
+                    let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {{\n                \
+                        label: Some(\"Compute Pass\"),\n                \
+                        timestamp_writes: None,\n            \
+                    }});\n            \
+                    compute_pass.set_pipeline(&self.compute_pipeline);\n\
+                {}            \
+                    compute_pass.dispatch_workgroups({}, {}, {});\n        \
+                }}\n\n",
+                if has_bind_group {
+                    "            compute_pass.set_bind_group(0, &self.bind_group, &[]);\n"
+                } else {
+                    ""
+                },
+                x,
+                y,
+                z
+            ));
+        }
+
+        // The pass always clears the window, and draws if there's a pipeline
+        let render_pipeline = Self::render_pipeline_state(state);
+        let multisampled = render_pipeline.is_some_and(|pipeline| pipeline.sample_count > 1);
+        let depth_stencil_attachment = match render_pipeline {
+            Some(pipeline) if pipeline.enable_depth_stencil => format!(
+                "Some(wgpu::RenderPassDepthStencilAttachment {{\n                    \
+                    view: &self.depth_view,\n                    \
+                    depth_ops: Some(wgpu::Operations {{\n                        \
+                        load: wgpu::LoadOp::Clear(1.0),\n                        \
+                        store: wgpu::StoreOp::Store,\n                    \
+                    }}),\n                    \
+                    stencil_ops: {},\n                \
+                }})",
+                if pipeline.depth_format.contains("Stencil") {
+                    "Some(wgpu::Operations {\n                        \
+                        load: wgpu::LoadOp::Clear(0),\n                        \
+                        store: wgpu::StoreOp::Store,\n                    \
+                    })"
+                } else {
+                    "None"
+                }
+            ),
+            _ => "None".to_string(),
+        };
+        let draw = match render_pipeline {
+            Some(_) => format!(
+                "\n            \
+                render_pass.set_pipeline(&self.render_pipeline);\n\
+                {}            \
+                render_pass.draw(0..3, 0..1);\n",
+                if has_bind_group {
+                    "            render_pass.set_bind_group(0, &self.bind_group, &[]);\n"
+                } else {
+                    ""
+                }
+            ),
+            None => String::new(),
+        };
+        code.push_str(&format!(
+            "        {{\n            \
+                {}let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {{\n                \
+                    label: Some(\"Render Pass\"),\n                \
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {{\n                    \
+                        view: {},\n                    \
+                        resolve_target: {},\n                    \
+                        ops: wgpu::Operations {{\n                        \
+                            load: wgpu::LoadOp::Clear(wgpu::Color {{\n                            \
+                                r: {:.2},\n                            \
+                                g: {:.2},\n                            \
+                                b: {:.2},\n                            \
+                                a: {:.2},\n                        \
+                            }}),\n                        \
+                            store: {},\n                    \
+                        }},\n                    \
+                        depth_slice: None,\n                \
+                    }})],\n                \
+                    depth_stencil_attachment: {},\n                \
+                    occlusion_query_set: None,\n                \
+                    timestamp_writes: None,\n                \
+                    multiview_mask: None,\n            \
+                }});\n\
+            {}        \
+            }}\n\n",
+            if render_pipeline.is_some() {
+                ""
+            } else {
+                "#[allow(unused_variables, unused_mut)]\n            "
+            },
+            if multisampled { "&self.msaa_view" } else { "&view" },
+            if multisampled { "Some(&view)" } else { "None" },
+            self.config.clear_color[0],
+            self.config.clear_color[1],
+            self.config.clear_color[2],
+            self.config.clear_color[3],
+            if multisampled {
+                "wgpu::StoreOp::Discard"
+            } else {
+                "wgpu::StoreOp::Store"
+            },
+            depth_stencil_attachment,
+            draw
+        ));
+
+        code.push_str(
+            "        self.queue.submit(std::iter::once(encoder.finish()));\n        \
+            output.present();\n    \
+            }\n",
+        );
+
+        code
+    }
+
+    /// Generate the entry point that opens the window and runs the event loop
+    ///
+    /// Web projects get a native `run` called by main.rs and a `start` that
+    /// renders into the page's canvas once the WASM module is loaded.
+    fn generate_main_function(&self) -> String {
+        let window_attributes = format!(
+            "    let event_loop = EventLoop::new().unwrap();\n    \
+            let window_attributes = Window::default_attributes()\n        \
+                .with_title(\"{}\")\n        \
+                .with_inner_size(winit::dpi::PhysicalSize::new({}, {}))",
+            self.config.project_name, self.config.canvas_width, self.config.canvas_height
+        );
+        let native = format!(
+            "{{\n    \
+                env_logger::init();\n\
+                \n\
+            {};\n\
+                \n    \
+                let window = Arc::new(event_loop.create_window(window_attributes).unwrap());\n\
+                \n    \
+                let mut state = pollster::block_on(State::new(window.clone()));\n\
+                \n    \
+                event_loop\n        \
+                    .run({})\n        \
+                    .unwrap();\n\
+            }}\n",
+            window_attributes, EVENT_HANDLER
+        );
+
+        match self.config.target {
+            ExportTarget::Native | ExportTarget::Cpp | ExportTarget::Python => {
+                format!("fn main() {}", native)
+            }
+            ExportTarget::Web => format!(
+                "/// Open a window and render until it is closed\n\
+                #[cfg(not(target_arch = \"wasm32\"))]\n\
+                pub fn run() {}\
+                \n\
+                /// Render into the page's `<canvas id=\"canvas\">` once the module is loaded\n\
+                #[cfg(target_arch = \"wasm32\")]\n\
+                #[wasm_bindgen::prelude::wasm_bindgen(start)]\n\
+                pub fn start() {{\n    \
+                    use wasm_bindgen::JsCast;\n    \
+                    use winit::platform::web::{{EventLoopExtWebSys, WindowAttributesExtWebSys}};\n\
+                    \n    \
+                    console_error_panic_hook::set_once();\n\
+                    \n    \
+                    let canvas = web_sys::window()\n        \
+                        .and_then(|window| window.document())\n        \
+                        .and_then(|document| document.get_element_by_id(\"canvas\"))\n        \
+                        .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok())\n        \
+                        .expect(\"The page has no <canvas id=\\\"canvas\\\">\");\n\
+                    \n\
+                {}\n        \
+                        .with_canvas(Some(canvas));\n\
+                    \n    \
+                    let window = Arc::new(event_loop.create_window(window_attributes).unwrap());\n\
+                    \n    \
+                    // The browser can't block on requesting the adapter and device\n    \
+                    wasm_bindgen_futures::spawn_local(async move {{\n        \
+                        let mut state = State::new(window).await;\n\
+                        \n        \
+                        event_loop.spawn({});\n    \
+                    }});\n\
+                }}\n",
+                native, window_attributes, EVENT_HANDLER
+            ),
+        }
+    }
+
+    /// Generate src/main.rs of a web project, which runs the library natively
+    fn generate_web_main(&self) -> String {
+        format!(
+            "fn main() {{\n    \
+                #[cfg(not(target_arch = \"wasm32\"))]\n    \
+                {}::run();\n\
+            }}\n",
+            self.crate_name()
+        )
+    }
+
+    /// Name the package's library is imported as
+    fn crate_name(&self) -> String {
+        self.config.project_name.replace('-', "_")
+    }
+
+    /// Generate the page that loads the WASM module of a web project
+    fn generate_index_html(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        let content = format!(
+            "<!DOCTYPE html>\n\
+            <html lang=\"en\">\n\
+            <head>\n    \
+                <meta charset=\"UTF-8\">\n    \
+                <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n    \
+                <title>{}</title>\n    \
+                <style>\n        \
+                    body {{\n            \
+                        margin: 0;\n            \
+                        min-height: 100vh;\n            \
+                        display: flex;\n            \
+                        flex-direction: column;\n            \
+                        justify-content: center;\n            \
+                        align-items: center;\n            \
+                        background: #1a1a1a;\n            \
+                        font-family: sans-serif;\n        \
+                    }}\n\
+                    \n        \
+                    #error {{\n            \
+                        color: #ff8080;\n        \
+                    }}\n    \
+                </style>\n\
+            </head>\n\
+            <body>\n    \
+                <canvas id=\"canvas\" width=\"{}\" height=\"{}\"></canvas>\n    \
+                <p id=\"error\" hidden></p>\n    \
+                <script type=\"module\">\n        \
+                    import init from './pkg/{}.js';\n\
+                    \n        \
+                    function showError(message) {{\n            \
+                        const error = document.getElementById('error');\n            \
+                        error.textContent = message;\n            \
+                        error.hidden = false;\n        \
+                    }}\n\
+                    \n        \
+                    if (!navigator.gpu) {{\n            \
+                        showError('This browser does not support WebGPU.');\n        \
+                    }}\n        \
+                    init().catch((e) => showError(`Failed to start: ${{e}}`));\n    \
+                </script>\n\
+            </body>\n\
+            </html>\n",
+            self.config.project_name,
+            self.config.canvas_width,
+            self.config.canvas_height,
+            self.crate_name()
+        );
+
+        std::fs::write(output_dir.join("index.html"), content)
+    }
+
+    /// Generate the script that builds the WASM module of a web project
+    fn generate_build_script(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        let path = output_dir.join("build.sh");
+        std::fs::write(
+            &path,
+            "#!/bin/sh\n\
+            # Build the WebAssembly module and its JavaScript bindings into pkg/\n\
+            set -e\n\
+            \n\
+            wasm-pack build --target web --release\n\
+            \n\
+            echo \"Built pkg/. Serve this directory over HTTP, e.g.:\"\n\
+            echo \"  python3 -m http.server 8000\"\n\
+            echo \"and open http://localhost:8000 in a browser with WebGPU support.\"\n",
+        )?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_code_gen_config_default() {
+        let config = CodeGenConfig::default();
+        assert_eq!(config.project_name, "wgpu_standalone");
+        assert_eq!(config.example_type, ExampleType::Triangle);
+        assert_eq!(config.canvas_width, 800);
+        assert_eq!(config.canvas_height, 600);
+    }
+
+    #[test]
+    fn test_code_gen_config_builder() {
+        let config = CodeGenConfig::new("my_project".to_string())
+            .with_canvas_size(1024, 768)
+            .with_clear_color([0.5, 0.5, 0.5, 1.0])
+            .with_example_type(ExampleType::Cube);
+
+        assert_eq!(config.project_name, "my_project");
+        assert_eq!(config.canvas_width, 1024);
+        assert_eq!(config.canvas_height, 768);
+        assert_eq!(config.clear_color, [0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(config.example_type, ExampleType::Cube);
+    }
+
+    #[test]
+    fn test_generate_project_structure() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_project");
+
+        // Clean up if exists
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let config = CodeGenConfig::new("test_project".to_string());
+        let generator = CodeGenerator::new(config);
+
+        generator.generate(&temp_dir).unwrap();
+
+        // Verify files were created
+        assert!(temp_dir.join("Cargo.toml").exists());
+        assert!(temp_dir.join("src").join("main.rs").exists());
+        assert!(temp_dir.join("README.md").exists());
+
+        // Clean up
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_cargo_toml_generation() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_cargo");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let config = CodeGenConfig::new("my_app".to_string());
+        let generator = CodeGenerator::new(config);
+        generator.generate_cargo_toml(&temp_dir).unwrap();
+
+        let cargo_toml = fs::read_to_string(temp_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"my_app\""));
+        assert!(cargo_toml.contains("wgpu = \"29.0\""));
+        assert!(cargo_toml.contains(
+            "winit = { version = \"0.30\", default-features = false, features = [\"rwh_06\", \"x11\"] }"
+        ));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_playground_state_export() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_playground");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        // Create a playground state with shader
+        let shader_code = "@vertex\nfn vs_main() -> @builtin(position) vec4<f32> {\n    return vec4<f32>(0.0, 0.0, 0.0, 1.0);\n}\n\n@fragment\nfn fs_main() -> @location(0) vec4<f32> {\n    return vec4<f32>(1.0, 0.0, 0.0, 1.0);\n}";
+
+        let playground_state = PlaygroundState {
+            version: "1.0".to_string(),
+            theme: crate::state::Theme::Dark,
+            language: None,
+            custom_theme: None,
+            shader_editor: Some(ShaderEditorState {
+                source_code: shader_code.to_string(),
+                label: "test_shader".to_string(),
+                file_path: "shader.wgsl".to_string(),
+                ..Default::default()
+            }),
+            buffer_panel: Some(BufferPanelState {
+                label: "vertex_buffer".to_string(),
+                size: "1024".to_string(),
+                usage_vertex: true,
+                usage_index: false,
+                usage_uniform: false,
+                usage_storage: false,
+                usage_indirect: false,
+                usage_copy_src: false,
+                usage_copy_dst: true,
+                usage_map_read: false,
+                usage_map_write: false,
+                usage_query_resolve: false,
+                mapped_at_creation: false,
+            }),
+            texture_panel: None,
+            sampler_panel: None,
+            render_pipeline_panel: None,
+            compute_pipeline_panel: None,
+            bind_group_panel: None,
+            bind_group_layout_panel: None,
+            api_coverage: None,
+            tutorial_state: None,
+            learning_progress: None,
+            device_config: None,
+        };
+
+        let config = CodeGenConfig::new("playground_export".to_string())
+            .with_playground_state(playground_state);
+
+        let generator = CodeGenerator::new(config);
+        generator.generate(&temp_dir).unwrap();
+
+        // Verify files were created
+        assert!(temp_dir.join("Cargo.toml").exists());
+        assert!(temp_dir.join("src").join("main.rs").exists());
+        assert!(temp_dir.join("README.md").exists());
+
+        // Verify main.rs contains the shader
+        let main_rs = fs::read_to_string(temp_dir.join("src").join("main.rs")).unwrap();
+        assert!(main_rs.contains("vs_main"));
+        assert!(main_rs.contains("fs_main"));
+        assert!(main_rs.contains("vertex_buffer"));
+
+        // Clean up
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_export_with_buffer_and_texture() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_full_export");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let playground_state = PlaygroundState {
+            version: "1.0".to_string(),
+            theme: crate::state::Theme::Dark,
+            language: None,
+            custom_theme: None,
+            shader_editor: Some(ShaderEditorState {
+                source_code: "@vertex\nfn main() {}".to_string(),
+                label: "shader".to_string(),
+                file_path: "shader.wgsl".to_string(),
+                ..Default::default()
+            }),
+            buffer_panel: Some(BufferPanelState {
+                label: "my_buffer".to_string(),
+                size: "256".to_string(),
+                usage_vertex: true,
+                usage_uniform: true,
+                usage_index: false,
+                usage_storage: false,
+                usage_indirect: false,
+                usage_copy_src: false,
+                usage_copy_dst: false,
+                usage_map_read: false,
+                usage_map_write: false,
+                usage_query_resolve: false,
+                mapped_at_creation: false,
+            }),
+            texture_panel: Some(TexturePanelState {
+                label: "my_texture".to_string(),
+                width: "512".to_string(),
+                height: "512".to_string(),
+                depth: "1".to_string(),
+                mip_levels: "1".to_string(),
+                sample_count: "1".to_string(),
+                format: "Rgba8Unorm".to_string(),
+                dimension: "D2".to_string(),
+                usage_copy_src: false,
+                usage_copy_dst: true,
+                usage_texture_binding: true,
+                usage_storage_binding: false,
+                usage_render_attachment: false,
+                image_data: None,
+            }),
+            sampler_panel: Some(SamplerPanelState {
+                label: "my_sampler".to_string(),
+                address_mode_u: "Repeat".to_string(),
+                address_mode_v: "Repeat".to_string(),
+                address_mode_w: "Repeat".to_string(),
+                mag_filter: "Linear".to_string(),
+                min_filter: "Linear".to_string(),
+                mipmap_filter: "Linear".to_string(),
+                lod_min_clamp: "0.0".to_string(),
+                lod_max_clamp: "32.0".to_string(),
+                compare: None,
+                max_anisotropy: "1".to_string(),
+            }),
+            render_pipeline_panel: None,
+            compute_pipeline_panel: None,
+            bind_group_panel: None,
+            bind_group_layout_panel: None,
+            api_coverage: None,
+            tutorial_state: None,
+            learning_progress: None,
+            device_config: None,
+        };
+
+        let config =
+            CodeGenConfig::new("full_export".to_string()).with_playground_state(playground_state);
+
+        let generator = CodeGenerator::new(config);
+        generator.generate(&temp_dir).unwrap();
+
+        // Verify main.rs contains all components
+        let main_rs = fs::read_to_string(temp_dir.join("src").join("main.rs")).unwrap();
+        assert!(main_rs.contains("my_buffer"));
+        assert!(main_rs.contains("my_texture"));
+        assert!(main_rs.contains("my_sampler"));
+        assert!(main_rs.contains("wgpu::BufferUsages::VERTEX"));
+        assert!(main_rs.contains("wgpu::BufferUsages::UNIFORM"));
+
+        // Clean up
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_compute_project_export() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_compute");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let shader = "@group(1) @binding(2)\n\
+            var<storage, read_write> counts: array<u32>;\n\
+            \n\
+            @compute @workgroup_size(32)\n\
+            fn count(@builtin(global_invocation_id) id: vec3<u32>) {\n    \
+                counts[id.x] = counts[id.x] + 1u;\n\
+            }\n";
+        let playground_state = PlaygroundState {
+            compute_pipeline_panel: Some(ComputePipelinePanelState {
+                label: "counter".to_string(),
+                entry_point: "count".to_string(),
+            }),
+            buffer_panel: Some(BufferPanelState {
+                label: "counts".to_string(),
+                size: "1022".to_string(),
+                usage_uniform: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let config = CodeGenConfig::new("compute_export".to_string())
+            .with_example_type(ExampleType::Compute)
+            .with_shader(shader.to_string())
+            .with_playground_state(playground_state);
+        CodeGenerator::new(config).generate(&temp_dir).unwrap();
+
+        let cargo_toml = fs::read_to_string(temp_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("wgpu = \"29.0\""));
+        assert!(!cargo_toml.contains("winit"));
+
+        let main_rs = fs::read_to_string(temp_dir.join("src").join("main.rs")).unwrap();
+        assert!(!main_rs.contains("winit"));
+        assert!(main_rs.contains("entry_point: Some(\"count\")"));
+        assert!(main_rs.contains("label: Some(\"counter\")"));
+        // Rounded up to whole values
+        assert!(main_rs.contains("const BUFFER_SIZE: u64 = 1024;"));
+        // 256 values in workgroups of 32
+        assert!(main_rs.contains("const WORKGROUPS: [u32; 3] = [8, 1, 1];"));
+        assert!(main_rs.contains("get_bind_group_layout(1)"));
+        assert!(main_rs.contains("binding: 2,"));
+        assert!(main_rs.contains("compute_pass.set_bind_group(1, &bind_group, &[]);"));
+        assert!(main_rs.contains("Vec<u32>"));
+        assert!(main_rs.contains("wgpu::BufferUsages::UNIFORM"));
+        assert!(main_rs.contains("copy_buffer_to_buffer"));
+
+        let readme = fs::read_to_string(temp_dir.join("README.md")).unwrap();
+        assert!(readme.contains("Dispatch: 8x1x1 workgroups"));
+        assert!(readme.contains("@group(1) @binding(2)"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_compute_project_defaults() {
+        let config = CodeGenConfig::new("compute_default".to_string())
+            .with_example_type(ExampleType::Compute)
+            .with_workgroups(3, 2, 1);
+        let generator = CodeGenerator::new(config);
+
+        let main_rs = generator.generate_compute_main().unwrap();
+        assert!(main_rs.contains(DEFAULT_COMPUTE_SHADER));
+        assert!(main_rs.contains("entry_point: Some(\"main\")"));
+        assert!(main_rs.contains("const WORKGROUPS: [u32; 3] = [3, 2, 1];"));
+        assert!(main_rs.contains("Vec<f32>"));
+        assert!(!generator.get_cargo_toml_content().contains("winit"));
+    }
+
+    #[test]
+    fn test_compute_project_rejects_invalid_shader() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_compute_invalid");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let config = CodeGenConfig::new("compute_invalid".to_string())
+            .with_example_type(ExampleType::Compute)
+            .with_playground_state(PlaygroundState {
+                compute_pipeline_panel: Some(ComputePipelinePanelState {
+                    label: String::new(),
+                    entry_point: "missing".to_string(),
+                }),
+                ..Default::default()
+            });
+        let err = CodeGenerator::new(config).generate(&temp_dir).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("'missing'"));
+        // Nothing is written for a project that can't be generated
+        assert!(!temp_dir.exists());
+
+        let config = CodeGenConfig::new("compute_invalid".to_string())
+            .with_example_type(ExampleType::Compute)
+            .with_shader("fn broken( {".to_string());
+        assert!(CodeGenerator::new(config).generate_compute_main().is_err());
+    }
+
+    #[test]
+    fn test_web_project_export() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_web");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let config = CodeGenConfig::new("web-demo".to_string())
+            .with_canvas_size(640, 480)
+            .with_target(ExportTarget::Web);
+        CodeGenerator::new(config).generate(&temp_dir).unwrap();
+
+        let cargo_toml = fs::read_to_string(temp_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("crate-type = [\"cdylib\", \"rlib\"]"));
+        assert!(cargo_toml.contains("wasm-bindgen = \"0.2\""));
+        assert!(cargo_toml.contains("\"HtmlCanvasElement\""));
+        assert!(cargo_toml.contains("[target.'cfg(not(target_arch = \"wasm32\"))'.dependencies]"));
+
+        let lib_rs = fs::read_to_string(temp_dir.join("src").join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("struct State"));
+        assert!(lib_rs.contains("pub fn run()"));
+        assert!(lib_rs.contains("#[wasm_bindgen::prelude::wasm_bindgen(start)]"));
+        assert!(lib_rs.contains(".with_canvas(Some(canvas))"));
+        assert!(lib_rs.contains("wasm_bindgen_futures::spawn_local"));
+        assert!(lib_rs.contains("event_loop.spawn(move |event, control_flow|"));
+        assert!(!lib_rs.contains("fn main()"));
+
+        // The binary runs the library natively
+        let main_rs = fs::read_to_string(temp_dir.join("src").join("main.rs")).unwrap();
+        assert!(main_rs.contains("web_demo::run();"));
+
+        let index_html = fs::read_to_string(temp_dir.join("index.html")).unwrap();
+        assert!(index_html.contains("import init from './pkg/web_demo.js';"));
+        assert!(index_html.contains("<canvas id=\"canvas\" width=\"640\" height=\"480\">"));
+
+        let build_sh = fs::read_to_string(temp_dir.join("build.sh")).unwrap();
+        assert!(build_sh.contains("wasm-pack build --target web --release"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(temp_dir.join("build.sh"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+
+        let readme = fs::read_to_string(temp_dir.join("README.md")).unwrap();
+        assert!(readme.contains("## Running in the Browser"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_native_project_has_no_web_files() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_native_target");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let config = CodeGenConfig::new("native_demo".to_string());
+        assert_eq!(config.target, ExportTarget::Native);
+        CodeGenerator::new(config).generate(&temp_dir).unwrap();
+
+        let main_rs = fs::read_to_string(temp_dir.join("src").join("main.rs")).unwrap();
+        assert!(main_rs.contains("fn main()"));
+        assert!(main_rs.contains("event_loop\n        .run(move |event, control_flow|"));
+        assert!(!main_rs.contains("wasm"));
+        assert!(!temp_dir.join("src").join("lib.rs").exists());
+        assert!(!temp_dir.join("index.html").exists());
+        assert!(!temp_dir.join("build.sh").exists());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_compute_project_rejects_web_target() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_compute_web");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let config = CodeGenConfig::new("compute_web".to_string())
+            .with_example_type(ExampleType::Compute)
+            .with_target(ExportTarget::Web);
+        let err = CodeGenerator::new(config).generate(&temp_dir).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!temp_dir.exists());
+    }
+}
@@ -0,0 +1,1531 @@
+//! Python backend of the code generator
+//!
+//! Writes a script using [wgpu-py](https://github.com/pygfx/wgpu-py) that
+//! recreates the playground setup and renders it into a window opened with
+//! rendercanvas, or runs a compute project headless and prints the buffer.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use super::{
+    parse_or, variant, CodeGenConfig, CodeGenerator, ExampleType, DEFAULT_BUFFER_SIZE,
+    DEFAULT_TEXTURE_DEPTH, DEFAULT_TEXTURE_HEIGHT, DEFAULT_TEXTURE_WIDTH, PRINTED_VALUES,
+    TEXTURE_DATA_FILE,
+};
+use crate::state::{
+    BindGroupEntryState, BufferPanelState, ComputePipelinePanelState, PlaygroundState,
+    RenderPipelinePanelState, SamplerPanelState, ShaderEditorState, TexturePanelState,
+};
+
+/// Longest line the layout of expressions aims for, black's default
+const LINE_LENGTH: usize = 88;
+
+/// Python expression, laid out on one line when it fits and otherwise with
+/// one element per line
+enum Expr {
+    Raw(String),
+    /// Function and its arguments, positional ones with an empty name
+    Call(String, Vec<(&'static str, Expr)>),
+    Dict(Vec<(&'static str, Expr)>),
+    List(Vec<Expr>),
+}
+
+impl Expr {
+    fn raw(code: impl Into<String>) -> Self {
+        Expr::Raw(code.into())
+    }
+
+    fn call(function: impl Into<String>, args: Vec<(&'static str, Expr)>) -> Self {
+        Expr::Call(function.into(), args)
+    }
+
+    /// Opening and closing brackets and the prefixed elements
+    fn parts(&self) -> (String, &'static str, Vec<(String, &Expr)>) {
+        match self {
+            Expr::Raw(_) => unreachable!("raw expressions have no elements"),
+            Expr::Call(function, args) => (
+                format!("{}(", function),
+                ")",
+                args.iter()
+                    .map(|(name, value)| match *name {
+                        "" => (String::new(), value),
+                        name => (format!("{}=", name), value),
+                    })
+                    .collect(),
+            ),
+            Expr::Dict(entries) => (
+                "{".to_string(),
+                "}",
+                entries
+                    .iter()
+                    .map(|(key, value)| (format!("\"{}\": ", key), value))
+                    .collect(),
+            ),
+            Expr::List(items) => (
+                "[".to_string(),
+                "]",
+                items.iter().map(|value| (String::new(), value)).collect(),
+            ),
+        }
+    }
+
+    fn one_line(&self) -> String {
+        if let Expr::Raw(code) = self {
+            return code.clone();
+        }
+        let (open, close, elements) = self.parts();
+        let elements: Vec<String> = elements
+            .into_iter()
+            .map(|(prefix, value)| prefix + &value.one_line())
+            .collect();
+        format!("{}{}{}", open, elements.join(", "), close)
+    }
+
+    /// The expression starting at `column` of a line indented by `indent`
+    fn render(&self, indent: usize, column: usize) -> String {
+        let line = self.one_line();
+        if matches!(self, Expr::Raw(_)) || column + line.len() < LINE_LENGTH {
+            return line;
+        }
+        let (open, close, elements) = self.parts();
+        let inner = indent + 4;
+        let mut code = open + "\n";
+        for (prefix, value) in elements {
+            code.push_str(&" ".repeat(inner));
+            code.push_str(&prefix);
+            code.push_str(&value.render(inner, inner + prefix.len()));
+            code.push_str(",\n");
+        }
+        code.push_str(&" ".repeat(indent));
+        code.push_str(close);
+        code
+    }
+}
+
+/// `name = value` at the given indentation
+fn assign(indent: usize, name: &str, value: Expr) -> String {
+    let prefix = format!("{}{} = ", " ".repeat(indent), name);
+    let value = value.render(indent, prefix.len());
+    format!("{}{}\n", prefix, value)
+}
+
+/// An expression statement at the given indentation
+fn statement(indent: usize, expr: Expr) -> String {
+    format!("{}{}\n", " ".repeat(indent), expr.render(indent, indent))
+}
+
+/// Python string literal of `value`
+fn string_literal(value: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn string(value: &str) -> Expr {
+    Expr::Raw(string_literal(value))
+}
+
+/// Words of a `Debug` name, each starting at an uppercase letter
+fn words(name: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, c) in name.char_indices().skip(1) {
+        if c.is_ascii_uppercase() {
+            words.push(&name[start..i]);
+            start = i;
+        }
+    }
+    words.push(&name[start..]);
+    words
+}
+
+/// wgpu-py enum member of type `ty` for a saved `Debug` name
+///
+/// wgpu-py names members after the WebGPU values with underscores, e.g.
+/// `OneMinusSrcAlpha` is `wgpu.BlendFactor.one_minus_src_alpha`.
+fn enum_member(ty: &str, name: &str, default: &str) -> Expr {
+    let member = words(variant(name, default))
+        .iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    Expr::Raw(format!("wgpu.{}.{}", ty, member))
+}
+
+/// wgpu-py member of a texture format saved as its `wgpu::TextureFormat`
+/// name
+///
+/// WebGPU only separates the sRGB and stencil suffixes of uncompressed
+/// formats, e.g. `Depth24PlusStencil8` is `depth24plus_stencil8`, while BC
+/// formats separate every word, e.g. `Bc1RgbaUnormSrgb` is
+/// `bc1_rgba_unorm_srgb`.
+fn texture_format(name: &str, default: &str) -> Expr {
+    let name = variant(name, default);
+    let mut member = String::new();
+    for (i, word) in words(name).into_iter().enumerate() {
+        let separated = name.starts_with("Bc")
+            || (i == 1 && (name.starts_with("Etc2") || name.starts_with("Eac")))
+            || word == "Srgb"
+            || word.starts_with("Stencil");
+        if i > 0 && separated {
+            member.push('_');
+        }
+        member.push_str(&word.to_ascii_lowercase());
+    }
+    Expr::Raw(format!("wgpu.TextureFormat.{}", member))
+}
+
+/// `a | b | c` of the enabled flags of a wgpu-py flag type, or 0 if none is
+fn flags(ty: &str, flags: &[(bool, &str)]) -> Expr {
+    let enabled: Vec<String> = flags
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, flag)| format!("wgpu.{}.{}", ty, flag))
+        .collect();
+    if enabled.is_empty() {
+        Expr::raw("0")
+    } else {
+        Expr::Raw(enabled.join(" | "))
+    }
+}
+
+fn bool_literal(value: bool) -> Expr {
+    Expr::raw(if value { "True" } else { "False" })
+}
+
+/// Shader stage of a pipeline, without the entry point if the pipeline
+/// doesn't name one so wgpu-py uses the shader's only one
+fn stage(entry_point: &str) -> Vec<(&'static str, Expr)> {
+    let mut stage = vec![("module", Expr::raw("shader_module"))];
+    if !entry_point.is_empty() {
+        stage.push(("entry_point", string(entry_point)));
+    }
+    stage
+}
+
+/// Writes the Python script of a render setup or compute project
+pub(super) struct PythonGenerator<'a> {
+    config: &'a CodeGenConfig,
+    /// The playground state, or a triangle drawn with the configured shader
+    state: Cow<'a, PlaygroundState>,
+    /// The texture's image, decoded to RGBA8
+    image: Option<image::RgbaImage>,
+}
+
+impl<'a> PythonGenerator<'a> {
+    pub(super) fn new(config: &'a CodeGenConfig) -> Self {
+        let state = CodeGenerator::state_or_triangle(config);
+        let image = state
+            .texture_panel
+            .as_ref()
+            .and_then(CodeGenerator::texture_image);
+        Self {
+            config,
+            state,
+            image,
+        }
+    }
+
+    /// Write main.py, requirements.txt, the texture's pixels and README.md
+    pub(super) fn generate(&self, output_dir: &Path) -> Result<(), std::io::Error> {
+        let compute = self.config.example_type == ExampleType::Compute;
+        let main_py = if compute {
+            self.compute_main_py()
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?
+        } else {
+            self.main_py()
+        };
+
+        std::fs::create_dir_all(output_dir)?;
+        std::fs::write(output_dir.join("main.py"), main_py)?;
+        let requirements = if compute {
+            "wgpu>=0.22\n"
+        } else {
+            "wgpu>=0.22\nrendercanvas>=2.0\nglfw>=2.5\n"
+        };
+        std::fs::write(output_dir.join("requirements.txt"), requirements)?;
+        if let (false, Some(image)) = (compute, &self.image) {
+            std::fs::write(output_dir.join(TEXTURE_DATA_FILE), image.as_raw())?;
+        }
+        std::fs::write(output_dir.join("README.md"), self.readme(compute))
+    }
+
+    /// Headless main.py that runs the compute dispatch and prints the buffer
+    fn compute_main_py(&self) -> Result<String, String> {
+        let setup = CodeGenerator::compute_setup(self.config)?;
+        let (element, typecode) = match setup.binding.as_ref().map_or("u32", |b| b.element) {
+            "f32" => ("f32", "f"),
+            "i32" => ("i32", "i"),
+            _ => ("u32", "I"),
+        };
+        let usage = setup
+            .usage
+            .iter()
+            .map(|flag| format!("wgpu.BufferUsage.{}", flag))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        let mut code = format!(
+            "\"\"\"Compute dispatch exported from wgpu_playground\"\"\"\n\
+            \n\
+            from array import array\n\
+            \n\
+            import wgpu\n\
+            \n\
+            SHADER_SOURCE = r\"\"\"{}\"\"\"\n\
+            \n\
+            # Size of the buffer in bytes\n\
+            BUFFER_SIZE = {}\n\
+            # Workgroups dispatched in X, Y and Z\n\
+            WORKGROUPS = ({}, {}, {})\n\
+            # Values printed from the start of the buffer\n\
+            PRINTED_VALUES = {}\n\
+            \n\
+            \n\
+            def main():\n    \
+                adapter = wgpu.gpu.request_adapter_sync(power_preference=\"high-performance\")\n    \
+                print(f\"Running on {{adapter.info['device']}}\")\n    \
+                device = adapter.request_device_sync(label=\"Device\")\n\
+            \n",
+            setup.shader_source,
+            setup.buffer_size,
+            setup.workgroups[0],
+            setup.workgroups[1],
+            setup.workgroups[2],
+            PRINTED_VALUES
+        );
+        code.push_str(&assign(
+            4,
+            "shader_module",
+            Expr::call(
+                "device.create_shader_module",
+                vec![
+                    ("label", string("Compute Shader")),
+                    ("code", Expr::raw("SHADER_SOURCE")),
+                ],
+            ),
+        ));
+        code.push_str(&format!(
+            "\n    # Fill the buffer with 0, 1, 2, ... as {}\n",
+            element
+        ));
+        code.push_str(&assign(
+            4,
+            "buffer",
+            Expr::call(
+                "device.create_buffer",
+                vec![
+                    ("label", string(&setup.buffer_label)),
+                    ("size", Expr::raw("BUFFER_SIZE")),
+                    ("usage", Expr::Raw(usage)),
+                ],
+            ),
+        ));
+        code.push_str(&format!(
+            "    device.queue.write_buffer(buffer, 0, array(\"{}\", range(BUFFER_SIZE // 4)))\n\
+            \n    \
+            # The layout is derived from the shader\n",
+            typecode
+        ));
+        code.push_str(&assign(
+            4,
+            "compute_pipeline",
+            Expr::call(
+                "device.create_compute_pipeline",
+                vec![
+                    ("label", string(&setup.pipeline_label)),
+                    ("layout", Expr::raw("wgpu.AutoLayoutMode.auto")),
+                    (
+                        "compute",
+                        Expr::Dict(vec![
+                            ("module", Expr::raw("shader_module")),
+                            ("entry_point", string(&setup.entry_point)),
+                        ]),
+                    ),
+                ],
+            ),
+        ));
+
+        let set_bind_group = match &setup.binding {
+            Some(binding) => {
+                code.push_str(&assign(
+                    4,
+                    "bind_group",
+                    Expr::call(
+                        "device.create_bind_group",
+                        vec![
+                            ("label", string("Bind Group")),
+                            (
+                                "layout",
+                                Expr::Raw(format!(
+                                    "compute_pipeline.get_bind_group_layout({})",
+                                    binding.group
+                                )),
+                            ),
+                            (
+                                "entries",
+                                Expr::List(vec![Expr::Dict(vec![
+                                    ("binding", Expr::Raw(binding.binding.to_string())),
+                                    (
+                                        "resource",
+                                        Expr::Dict(vec![
+                                            ("buffer", Expr::raw("buffer")),
+                                            ("offset", Expr::raw("0")),
+                                            ("size", Expr::raw("BUFFER_SIZE")),
+                                        ]),
+                                    ),
+                                ])]),
+                            ),
+                        ],
+                    ),
+                ));
+                format!(
+                    "    compute_pass.set_bind_group({}, bind_group)\n",
+                    binding.group
+                )
+            }
+            None => {
+                code.push_str(
+                    "    # The entry point uses no storage buffer, so the buffer is only\n    \
+                    # read back unchanged\n",
+                );
+                String::new()
+            }
+        };
+
+        code.push_str(&format!(
+            "\n    \
+            command_encoder = device.create_command_encoder(label=\"Compute Encoder\")\n    \
+            compute_pass = command_encoder.begin_compute_pass(label=\"Compute Pass\")\n    \
+            compute_pass.set_pipeline(compute_pipeline)\n\
+            {}    \
+            compute_pass.dispatch_workgroups(*WORKGROUPS)\n    \
+            compute_pass.end()\n    \
+            device.queue.submit([command_encoder.finish()])\n\
+            \n    \
+            output = device.queue.read_buffer(buffer).cast(\"{}\")\n    \
+            print(\"dispatch_workgroups({{}}, {{}}, {{}})\".format(*WORKGROUPS))\n    \
+            print(f\"{{buffer.label}} ({{len(output)}} values):\")\n    \
+            for index, value in enumerate(output[:PRINTED_VALUES]):\n        \
+                print(f\"  [{{index}}] {{value}}\")\n    \
+            if len(output) > PRINTED_VALUES:\n        \
+                print(f\"  ... {{len(output) - PRINTED_VALUES}} more\")\n\
+            \n\
+            \n\
+            if __name__ == \"__main__\":\n    \
+                main()\n",
+            set_bind_group, typecode
+        ));
+        Ok(code)
+    }
+
+    /// main.py recreating the playground setup
+    fn main_py(&self) -> String {
+        let state = self.state.as_ref();
+        let mut code = format!(
+            "\"\"\"{} exported from wgpu_playground\"\"\"\n\
+            \n",
+            self.config.project_name
+        );
+        if self.image.is_some() {
+            code.push_str("from pathlib import Path\n\n");
+        }
+        code.push_str("import wgpu\nfrom rendercanvas.auto import RenderCanvas, loop\n\n");
+
+        // WGSL has no string literals, so the raw string can't end early
+        if let Some(shader_editor) = &state.shader_editor {
+            code.push_str(&format!(
+                "SHADER_SOURCE = r\"\"\"{}\"\"\"\n\n",
+                shader_editor.source_code
+            ));
+        }
+        if let Some(image) = &self.image {
+            code.push_str(&format!(
+                "# RGBA8 pixels of the image loaded in the playground, {}x{}\n\
+                TEXTURE_DATA = (Path(__file__).parent / \"{}\").read_bytes()\n\n",
+                image.width(),
+                image.height(),
+                TEXTURE_DATA_FILE
+            ));
+        }
+        if CodeGenerator::needs_attachments(state) {
+            code.push_str(
+                "\n\
+                def create_attachment(device, size, texture_format, sample_count, label):\n    \
+                    \"\"\"Render target of the given size\"\"\"\n    \
+                    texture = device.create_texture(\n        \
+                        label=label,\n        \
+                        size=(size[0], size[1], 1),\n        \
+                        sample_count=sample_count,\n        \
+                        format=texture_format,\n        \
+                        usage=wgpu.TextureUsage.RENDER_ATTACHMENT,\n    \
+                    )\n    \
+                    return texture.create_view()\n\n",
+            );
+        }
+
+        code.push_str(&format!(
+            "\n\
+            def main():\n    \
+                canvas = RenderCanvas(size=({}, {}), title={})\n    \
+                adapter = wgpu.gpu.request_adapter_sync(power_preference=\"high-performance\")\n",
+            self.config.canvas_width,
+            self.config.canvas_height,
+            string_literal(&self.config.project_name)
+        ));
+        let features = self.required_features();
+        let mut device_args = vec![("label", string("Device"))];
+        if !features.is_empty() {
+            device_args.push((
+                "required_features",
+                Expr::List(features.into_iter().map(string).collect()),
+            ));
+        }
+        code.push_str(&assign(
+            4,
+            "device",
+            Expr::call("adapter.request_device_sync", device_args),
+        ));
+        code.push_str(
+            "    context = canvas.get_context(\"wgpu\")\n    \
+            surface_format = context.get_preferred_format(adapter)\n    \
+            context.configure(device=device, format=surface_format)\n\n",
+        );
+
+        if let Some(buffer_state) = &state.buffer_panel {
+            code.push_str(&Self::buffer_creation(buffer_state));
+        }
+        if let Some(texture_state) = &state.texture_panel {
+            code.push_str(&self.texture_creation(texture_state));
+        }
+        if let Some(sampler_state) = &state.sampler_panel {
+            code.push_str(&Self::sampler_creation(sampler_state));
+        }
+        if let Some(shader_state) = &state.shader_editor {
+            code.push_str(&Self::shader_module_creation(shader_state));
+        }
+        let has_bind_group = !CodeGenerator::bind_group_entries(state).is_empty();
+        if has_bind_group {
+            code.push_str(&self.bind_group_creation());
+        }
+
+        // Both pipelines share a layout with the bind group at index 0
+        let render_pipeline = CodeGenerator::render_pipeline_state(state);
+        let compute_pipeline = CodeGenerator::compute_pipeline_state(state);
+        if render_pipeline.is_some() || compute_pipeline.is_some() {
+            let layouts = if has_bind_group {
+                vec![Expr::raw("bind_group_layout")]
+            } else {
+                Vec::new()
+            };
+            code.push_str(&assign(
+                4,
+                "pipeline_layout",
+                Expr::call(
+                    "device.create_pipeline_layout",
+                    vec![
+                        ("label", string("Pipeline Layout")),
+                        ("bind_group_layouts", Expr::List(layouts)),
+                    ],
+                ),
+            ));
+            code.push('\n');
+        }
+        if let Some(pipeline_state) = render_pipeline {
+            code.push_str(&Self::render_pipeline_creation(pipeline_state));
+        }
+        if let Some(compute_state) = compute_pipeline {
+            code.push_str(&Self::compute_pipeline_creation(compute_state));
+        }
+
+        code.push_str(&self.draw_frame());
+        code.push_str(
+            "    canvas.request_draw(draw_frame)\n    \
+            loop.run()\n\
+            \n\
+            \n\
+            if __name__ == \"__main__\":\n    \
+                main()\n",
+        );
+        code
+    }
+
+    /// WebGPU names of the features the configuration needs
+    fn required_features(&self) -> Vec<&'static str> {
+        let state = self.state.as_ref();
+        let mut features = Vec::new();
+        if let Some(pipeline) = CodeGenerator::render_pipeline_state(state) {
+            if pipeline.enable_depth_stencil && pipeline.depth_format == "Depth32FloatStencil8" {
+                features.push("depth32float-stencil8");
+            }
+        }
+        if let Some(texture) = &state.texture_panel {
+            if texture.format.starts_with("Bc") {
+                features.push("texture-compression-bc");
+            }
+        }
+        features
+    }
+
+    /// Buffer creation code
+    fn buffer_creation(buffer_state: &BufferPanelState) -> String {
+        let mut size = parse_or(&buffer_state.size, DEFAULT_BUFFER_SIZE, "buffer size");
+        // Buffers mapped at creation need a size aligned to 4 bytes
+        if buffer_state.mapped_at_creation {
+            size = size.next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+        }
+        let usage = flags(
+            "BufferUsage",
+            &[
+                (buffer_state.usage_vertex, "VERTEX"),
+                (buffer_state.usage_index, "INDEX"),
+                (buffer_state.usage_uniform, "UNIFORM"),
+                (buffer_state.usage_storage, "STORAGE"),
+                (buffer_state.usage_indirect, "INDIRECT"),
+                (buffer_state.usage_copy_src, "COPY_SRC"),
+                (buffer_state.usage_copy_dst, "COPY_DST"),
+                (buffer_state.usage_map_read, "MAP_READ"),
+                (buffer_state.usage_map_write, "MAP_WRITE"),
+                (buffer_state.usage_query_resolve, "QUERY_RESOLVE"),
+            ],
+        );
+        let mut code = assign(
+            4,
+            "buffer",
+            Expr::call(
+                "device.create_buffer",
+                vec![
+                    ("label", string(&buffer_state.label)),
+                    ("size", Expr::Raw(size.to_string())),
+                    ("usage", usage),
+                    (
+                        "mapped_at_creation",
+                        bool_literal(buffer_state.mapped_at_creation),
+                    ),
+                ],
+            ),
+        );
+        if buffer_state.mapped_at_creation {
+            code.push_str("    buffer.unmap()\n");
+        }
+        code.push('\n');
+        code
+    }
+
+    /// Texture creation code, uploading the loaded image if any
+    fn texture_creation(&self, texture_state: &TexturePanelState) -> String {
+        let (width, height, depth) = match &self.image {
+            Some(image) => (image.width(), image.height(), 1),
+            None => (
+                parse_or(&texture_state.width, DEFAULT_TEXTURE_WIDTH, "texture width"),
+                parse_or(
+                    &texture_state.height,
+                    DEFAULT_TEXTURE_HEIGHT,
+                    "texture height",
+                ),
+                parse_or(&texture_state.depth, DEFAULT_TEXTURE_DEPTH, "texture depth"),
+            ),
+        };
+        let mip_levels = parse_or(&texture_state.mip_levels, 1, "texture mip level count");
+        let sample_count = parse_or(&texture_state.sample_count, 1, "texture sample count");
+        let dimension = match variant(&texture_state.dimension, "D2") {
+            "D1" => "wgpu.TextureDimension.d1",
+            "D3" => "wgpu.TextureDimension.d3",
+            _ => "wgpu.TextureDimension.d2",
+        };
+        let usage = flags(
+            "TextureUsage",
+            &[
+                (texture_state.usage_copy_src, "COPY_SRC"),
+                // The image is written to the texture
+                (
+                    texture_state.usage_copy_dst || self.image.is_some(),
+                    "COPY_DST",
+                ),
+                (texture_state.usage_texture_binding, "TEXTURE_BINDING"),
+                (texture_state.usage_storage_binding, "STORAGE_BINDING"),
+                (texture_state.usage_render_attachment, "RENDER_ATTACHMENT"),
+            ],
+        );
+
+        let mut code = assign(
+            4,
+            "texture",
+            Expr::call(
+                "device.create_texture",
+                vec![
+                    ("label", string(&texture_state.label)),
+                    (
+                        "size",
+                        Expr::Raw(format!("({}, {}, {})", width, height, depth)),
+                    ),
+                    ("mip_level_count", Expr::Raw(mip_levels.to_string())),
+                    ("sample_count", Expr::Raw(sample_count.to_string())),
+                    ("dimension", Expr::raw(dimension)),
+                    (
+                        "format",
+                        texture_format(&texture_state.format, "Rgba8UnormSrgb"),
+                    ),
+                    ("usage", usage),
+                ],
+            ),
+        );
+        if self.image.is_some() {
+            code.push_str(&statement(
+                4,
+                Expr::call(
+                    "device.queue.write_texture",
+                    vec![
+                        ("", Expr::Dict(vec![("texture", Expr::raw("texture"))])),
+                        ("", Expr::raw("TEXTURE_DATA")),
+                        (
+                            "",
+                            Expr::Dict(vec![
+                                ("bytes_per_row", Expr::Raw((width * 4).to_string())),
+                                ("rows_per_image", Expr::Raw(height.to_string())),
+                            ]),
+                        ),
+                        ("", Expr::Raw(format!("({}, {}, 1)", width, height))),
+                    ],
+                ),
+            ));
+        }
+        code.push_str("    texture_view = texture.create_view()\n\n");
+        code
+    }
+
+    /// Sampler creation code
+    fn sampler_creation(sampler_state: &SamplerPanelState) -> String {
+        let lod_min_clamp = parse_or(&sampler_state.lod_min_clamp, 0.0f32, "LOD min clamp");
+        let lod_max_clamp = parse_or(&sampler_state.lod_max_clamp, 32.0f32, "LOD max clamp");
+        let anisotropy = parse_or(&sampler_state.max_anisotropy, 1u16, "anisotropy").clamp(1, 16);
+        let address_mode = |name: &str| {
+            if name == "ClampToBorder" {
+                log::warn!("WebGPU has no ClampToBorder address mode. Using ClampToEdge.");
+                return enum_member("AddressMode", "ClampToEdge", "ClampToEdge");
+            }
+            enum_member("AddressMode", name, "ClampToEdge")
+        };
+        let compare = match &sampler_state.compare {
+            Some(compare) => enum_member("CompareFunction", compare, "LessEqual"),
+            None => Expr::raw("None"),
+        };
+
+        let mut code = assign(
+            4,
+            "sampler",
+            Expr::call(
+                "device.create_sampler",
+                vec![
+                    ("label", string(&sampler_state.label)),
+                    (
+                        "address_mode_u",
+                        address_mode(&sampler_state.address_mode_u),
+                    ),
+                    (
+                        "address_mode_v",
+                        address_mode(&sampler_state.address_mode_v),
+                    ),
+                    (
+                        "address_mode_w",
+                        address_mode(&sampler_state.address_mode_w),
+                    ),
+                    (
+                        "mag_filter",
+                        enum_member("FilterMode", &sampler_state.mag_filter, "Linear"),
+                    ),
+                    (
+                        "min_filter",
+                        enum_member("FilterMode", &sampler_state.min_filter, "Linear"),
+                    ),
+                    (
+                        "mipmap_filter",
+                        enum_member("MipmapFilterMode", &sampler_state.mipmap_filter, "Linear"),
+                    ),
+                    ("lod_min_clamp", Expr::Raw(format!("{:?}", lod_min_clamp))),
+                    ("lod_max_clamp", Expr::Raw(format!("{:?}", lod_max_clamp))),
+                    ("compare", compare),
+                    ("max_anisotropy", Expr::Raw(anisotropy.to_string())),
+                ],
+            ),
+        );
+        code.push('\n');
+        code
+    }
+
+    /// Shader module creation code
+    fn shader_module_creation(shader_state: &ShaderEditorState) -> String {
+        let mut code = assign(
+            4,
+            "shader_module",
+            Expr::call(
+                "device.create_shader_module",
+                vec![
+                    ("label", string(&shader_state.label)),
+                    ("code", Expr::raw("SHADER_SOURCE")),
+                ],
+            ),
+        );
+        code.push('\n');
+        code
+    }
+
+    /// The bind group layout and the bind group
+    ///
+    /// As in the Rust projects, entries no configured resource fits get a
+    /// placeholder.
+    fn bind_group_creation(&self) -> String {
+        let state = self.state.as_ref();
+        let label = state
+            .bind_group_panel
+            .as_ref()
+            .filter(|panel| !panel.entries.is_empty())
+            .map(|panel| panel.label.as_str())
+            .or_else(|| {
+                state
+                    .bind_group_layout_panel
+                    .as_ref()
+                    .map(|p| p.label.as_str())
+            })
+            .filter(|label| !label.is_empty())
+            .unwrap_or("Bind Group");
+
+        let mut layout_entries = Vec::new();
+        let mut group_entries = Vec::new();
+        let mut placeholders = String::new();
+        for entry in CodeGenerator::bind_group_entries(state) {
+            let visibility = flags(
+                "ShaderStage",
+                &[
+                    (entry.visibility_vertex, "VERTEX"),
+                    (entry.visibility_fragment, "FRAGMENT"),
+                    (entry.visibility_compute, "COMPUTE"),
+                ],
+            );
+            let (key, layout, resource) = self.bind_group_entry(entry, &mut placeholders);
+            let binding = Expr::Raw(entry.binding.to_string());
+            layout_entries.push(Expr::Dict(vec![
+                ("binding", binding),
+                ("visibility", visibility),
+                (key, layout),
+            ]));
+            group_entries.push(Expr::Dict(vec![
+                ("binding", Expr::Raw(entry.binding.to_string())),
+                ("resource", resource),
+            ]));
+        }
+
+        let mut code = assign(
+            4,
+            "bind_group_layout",
+            Expr::call(
+                "device.create_bind_group_layout",
+                vec![
+                    ("label", string(&format!("{} Layout", label))),
+                    ("entries", Expr::List(layout_entries)),
+                ],
+            ),
+        );
+        code.push_str(&placeholders);
+        code.push_str(&assign(
+            4,
+            "bind_group",
+            Expr::call(
+                "device.create_bind_group",
+                vec![
+                    ("label", string(label)),
+                    ("layout", Expr::raw("bind_group_layout")),
+                    ("entries", Expr::List(group_entries)),
+                ],
+            ),
+        ));
+        code.push('\n');
+        code
+    }
+
+    /// Binding type key and layout of a layout entry and resource of a bind
+    /// group entry, adding a placeholder resource when no configured
+    /// resource fits
+    fn bind_group_entry(
+        &self,
+        entry: &BindGroupEntryState,
+        placeholders: &mut String,
+    ) -> (&'static str, Expr, Expr) {
+        let state = self.state.as_ref();
+        let binding = entry.binding;
+        let fits = CodeGenerator::configured_resource_fits(state, entry);
+        let buffer_resource = |buffer: &str| {
+            Expr::Dict(vec![
+                ("buffer", Expr::raw(buffer)),
+                ("offset", Expr::raw("0")),
+                ("size", Expr::Raw(format!("{}.size", buffer))),
+            ])
+        };
+
+        match entry.binding_type.as_str() {
+            "UniformBuffer" | "StorageBuffer" => {
+                let (ty, usage) = match entry.binding_type.as_str() {
+                    "UniformBuffer" => ("uniform", "UNIFORM"),
+                    _ if entry.read_only => ("read_only_storage", "STORAGE"),
+                    _ => ("storage", "STORAGE"),
+                };
+                let buffer = if fits {
+                    "buffer".to_string()
+                } else {
+                    let name = format!("binding_{}_buffer", binding);
+                    placeholders.push_str(&format!(
+                        "    # No configured buffer fits binding {}\n",
+                        binding
+                    ));
+                    placeholders.push_str(&assign(
+                        4,
+                        &name,
+                        Expr::call(
+                            "device.create_buffer",
+                            vec![
+                                ("label", string(&format!("Binding {} Buffer", binding))),
+                                ("size", Expr::Raw(DEFAULT_BUFFER_SIZE.to_string())),
+                                (
+                                    "usage",
+                                    Expr::Raw(format!(
+                                        "wgpu.BufferUsage.{} | wgpu.BufferUsage.COPY_DST",
+                                        usage
+                                    )),
+                                ),
+                            ],
+                        ),
+                    ));
+                    name
+                };
+                (
+                    "buffer",
+                    Expr::Dict(vec![(
+                        "type",
+                        Expr::Raw(format!("wgpu.BufferBindingType.{}", ty)),
+                    )]),
+                    buffer_resource(&buffer),
+                )
+            }
+            "Texture" => {
+                let view = if fits {
+                    "texture_view".to_string()
+                } else {
+                    Self::placeholder_texture(
+                        binding,
+                        Expr::raw("wgpu.TextureFormat.rgba8unorm"),
+                        "TEXTURE_BINDING",
+                        placeholders,
+                    )
+                };
+                (
+                    "texture",
+                    Expr::Dict(vec![
+                        ("sample_type", Expr::raw("wgpu.TextureSampleType.float")),
+                        ("view_dimension", Expr::raw("wgpu.TextureViewDimension.d2")),
+                    ]),
+                    Expr::Raw(view),
+                )
+            }
+            "Sampler" => {
+                let sampler = if fits {
+                    "sampler".to_string()
+                } else {
+                    let name = format!("binding_{}_sampler", binding);
+                    placeholders.push_str(&format!(
+                        "    # No configured sampler fits binding {}\n",
+                        binding
+                    ));
+                    placeholders.push_str(&assign(
+                        4,
+                        &name,
+                        Expr::call(
+                            "device.create_sampler",
+                            vec![
+                                ("label", string(&format!("Binding {} Sampler", binding))),
+                                ("mag_filter", Expr::raw("wgpu.FilterMode.linear")),
+                                ("min_filter", Expr::raw("wgpu.FilterMode.linear")),
+                            ],
+                        ),
+                    ));
+                    name
+                };
+                (
+                    "sampler",
+                    Expr::Dict(vec![(
+                        "type",
+                        Expr::raw("wgpu.SamplerBindingType.filtering"),
+                    )]),
+                    Expr::Raw(sampler),
+                )
+            }
+            _ => {
+                let (access, format) = CodeGenerator::storage_texture_binding(entry);
+                let access = enum_member("StorageTextureAccess", &format!("{:?}", access), "");
+                let view = if fits {
+                    "texture_view".to_string()
+                } else {
+                    Self::placeholder_texture(
+                        binding,
+                        texture_format(&format, "Rgba8Unorm"),
+                        "STORAGE_BINDING",
+                        placeholders,
+                    )
+                };
+                (
+                    "storage_texture",
+                    Expr::Dict(vec![
+                        ("access", access),
+                        ("format", texture_format(&format, "Rgba8Unorm")),
+                        ("view_dimension", Expr::raw("wgpu.TextureViewDimension.d2")),
+                    ]),
+                    Expr::Raw(view),
+                )
+            }
+        }
+    }
+
+    /// Add a placeholder texture for a binding and return its view
+    fn placeholder_texture(
+        binding: u32,
+        format: Expr,
+        usage: &str,
+        placeholders: &mut String,
+    ) -> String {
+        let name = format!("binding_{}_view", binding);
+        placeholders.push_str(&format!(
+            "    # No configured texture fits binding {}\n",
+            binding
+        ));
+        placeholders.push_str(&assign(
+            4,
+            &format!("binding_{}_texture", binding),
+            Expr::call(
+                "device.create_texture",
+                vec![
+                    ("label", string(&format!("Binding {} Texture", binding))),
+                    (
+                        "size",
+                        Expr::Raw(format!(
+                            "({}, {}, 1)",
+                            DEFAULT_TEXTURE_WIDTH, DEFAULT_TEXTURE_HEIGHT
+                        )),
+                    ),
+                    ("format", format),
+                    ("usage", Expr::Raw(format!("wgpu.TextureUsage.{}", usage))),
+                ],
+            ),
+        ));
+        placeholders.push_str(&format!(
+            "    {} = binding_{}_texture.create_view()\n",
+            name, binding
+        ));
+        name
+    }
+
+    /// Render pipeline creation code
+    fn render_pipeline_creation(pipeline_state: &RenderPipelinePanelState) -> String {
+        let sample_count = pipeline_state.sample_count.max(1);
+        if !matches!(pipeline_state.polygon_mode.as_str(), "Fill" | "") {
+            log::warn!(
+                "WebGPU has no polygon modes. Exporting the {} polygon mode as Fill.",
+                pipeline_state.polygon_mode
+            );
+        }
+        let write_mask = match [
+            pipeline_state.write_red,
+            pipeline_state.write_green,
+            pipeline_state.write_blue,
+            pipeline_state.write_alpha,
+        ] {
+            [true, true, true, true] => Expr::raw("wgpu.ColorWrite.ALL"),
+            _ => flags(
+                "ColorWrite",
+                &[
+                    (pipeline_state.write_red, "RED"),
+                    (pipeline_state.write_green, "GREEN"),
+                    (pipeline_state.write_blue, "BLUE"),
+                    (pipeline_state.write_alpha, "ALPHA"),
+                ],
+            ),
+        };
+
+        let mut target = vec![("format", Expr::raw("surface_format"))];
+        if pipeline_state.blend_enabled {
+            let component = |src: &str, dst: &str, op: &str| {
+                Expr::Dict(vec![
+                    ("src_factor", enum_member("BlendFactor", src, "One")),
+                    ("dst_factor", enum_member("BlendFactor", dst, "Zero")),
+                    ("operation", enum_member("BlendOperation", op, "Add")),
+                ])
+            };
+            target.push((
+                "blend",
+                Expr::Dict(vec![
+                    (
+                        "color",
+                        component(
+                            &pipeline_state.color_blend_src,
+                            &pipeline_state.color_blend_dst,
+                            &pipeline_state.color_blend_op,
+                        ),
+                    ),
+                    (
+                        "alpha",
+                        component(
+                            &pipeline_state.alpha_blend_src,
+                            &pipeline_state.alpha_blend_dst,
+                            &pipeline_state.alpha_blend_op,
+                        ),
+                    ),
+                ]),
+            ));
+        }
+        target.push(("write_mask", write_mask));
+        let mut fragment = stage(&pipeline_state.fragment_entry_point);
+        fragment.push(("targets", Expr::List(vec![Expr::Dict(target)])));
+        let mut vertex = stage(&pipeline_state.vertex_entry_point);
+        vertex.push(("buffers", Expr::List(Vec::new())));
+
+        let mut args = vec![
+            ("label", string(&pipeline_state.label)),
+            ("layout", Expr::raw("pipeline_layout")),
+            ("vertex", Expr::Dict(vertex)),
+            (
+                "primitive",
+                Expr::Dict(vec![
+                    (
+                        "topology",
+                        enum_member(
+                            "PrimitiveTopology",
+                            &pipeline_state.topology,
+                            "TriangleList",
+                        ),
+                    ),
+                    (
+                        "front_face",
+                        enum_member("FrontFace", &pipeline_state.front_face, "Ccw"),
+                    ),
+                    (
+                        "cull_mode",
+                        enum_member("CullMode", &pipeline_state.cull_mode, "None"),
+                    ),
+                ]),
+            ),
+        ];
+        if pipeline_state.enable_depth_stencil {
+            let mask = |input: &str| {
+                u32::from_str_radix(input.trim_start_matches("0x"), 16).unwrap_or(0xFFFFFFFF)
+            };
+            let face = |compare: &str, fail: &str, depth_fail: &str, pass: &str| {
+                Expr::Dict(vec![
+                    ("compare", enum_member("CompareFunction", compare, "Always")),
+                    ("fail_op", enum_member("StencilOperation", fail, "Keep")),
+                    (
+                        "depth_fail_op",
+                        enum_member("StencilOperation", depth_fail, "Keep"),
+                    ),
+                    ("pass_op", enum_member("StencilOperation", pass, "Keep")),
+                ])
+            };
+            args.push((
+                "depth_stencil",
+                Expr::Dict(vec![
+                    (
+                        "format",
+                        texture_format(&pipeline_state.depth_format, "Depth24Plus"),
+                    ),
+                    (
+                        "depth_write_enabled",
+                        bool_literal(pipeline_state.depth_write_enabled),
+                    ),
+                    (
+                        "depth_compare",
+                        enum_member("CompareFunction", &pipeline_state.depth_compare, "Less"),
+                    ),
+                    (
+                        "stencil_front",
+                        face(
+                            &pipeline_state.stencil_front_compare,
+                            &pipeline_state.stencil_front_fail_op,
+                            &pipeline_state.stencil_front_depth_fail_op,
+                            &pipeline_state.stencil_front_pass_op,
+                        ),
+                    ),
+                    (
+                        "stencil_back",
+                        face(
+                            &pipeline_state.stencil_back_compare,
+                            &pipeline_state.stencil_back_fail_op,
+                            &pipeline_state.stencil_back_depth_fail_op,
+                            &pipeline_state.stencil_back_pass_op,
+                        ),
+                    ),
+                    (
+                        "stencil_read_mask",
+                        Expr::Raw(format!("{:#X}", mask(&pipeline_state.stencil_read_mask))),
+                    ),
+                    (
+                        "stencil_write_mask",
+                        Expr::Raw(format!("{:#X}", mask(&pipeline_state.stencil_write_mask))),
+                    ),
+                ]),
+            ));
+        }
+        args.push((
+            "multisample",
+            Expr::Dict(vec![
+                ("count", Expr::Raw(sample_count.to_string())),
+                ("mask", Expr::raw("0xFFFFFFFF")),
+                (
+                    "alpha_to_coverage_enabled",
+                    bool_literal(pipeline_state.alpha_to_coverage_enabled && sample_count > 1),
+                ),
+            ]),
+        ));
+        args.push(("fragment", Expr::Dict(fragment)));
+
+        let mut code = String::from(
+            "    # The color target uses the surface format rather than the pipeline\n    \
+            # panel's target format, so the pipeline can draw to the window\n",
+        );
+        code.push_str(&assign(
+            4,
+            "render_pipeline",
+            Expr::call("device.create_render_pipeline", args),
+        ));
+        code.push('\n');
+        code
+    }
+
+    /// Compute pipeline creation code
+    fn compute_pipeline_creation(compute_state: &ComputePipelinePanelState) -> String {
+        let mut code = assign(
+            4,
+            "compute_pipeline",
+            Expr::call(
+                "device.create_compute_pipeline",
+                vec![
+                    ("label", string(&compute_state.label)),
+                    ("layout", Expr::raw("pipeline_layout")),
+                    ("compute", Expr::Dict(stage(&compute_state.entry_point))),
+                ],
+            ),
+        );
+        code.push('\n');
+        code
+    }
+
+    /// `draw_frame`, which dispatches the compute pipeline, then clears the
+    /// window and draws a full-screen triangle with the render pipeline
+    fn draw_frame(&self) -> String {
+        let state = self.state.as_ref();
+        let has_bind_group = !CodeGenerator::bind_group_entries(state).is_empty();
+        let render_pipeline = CodeGenerator::render_pipeline_state(state);
+        let depth = render_pipeline.filter(|pipeline| pipeline.enable_depth_stencil);
+        let sample_count = render_pipeline.map_or(1, |pipeline| pipeline.sample_count.max(1));
+
+        let mut code = String::new();
+        if CodeGenerator::needs_attachments(state) {
+            code.push_str("    # Render targets matching the window, recreated when it resizes\n");
+            if depth.is_some() {
+                code.push_str("    depth_view = None\n");
+            }
+            if sample_count > 1 {
+                code.push_str("    msaa_view = None\n");
+            }
+            code.push_str("    attachment_size = None\n\n");
+        }
+        code.push_str("    def draw_frame():\n");
+        if CodeGenerator::needs_attachments(state) {
+            let mut names = Vec::new();
+            if depth.is_some() {
+                names.push("depth_view");
+            }
+            if sample_count > 1 {
+                names.push("msaa_view");
+            }
+            names.push("attachment_size");
+            code.push_str(&format!("        nonlocal {}\n", names.join(", ")));
+        }
+        code.push_str(
+            "        current_texture = context.get_current_texture()\n        \
+            view = current_texture.create_view()\n",
+        );
+        if CodeGenerator::needs_attachments(state) {
+            code.push_str(
+                "        if current_texture.size[:2] != attachment_size:\n            \
+                attachment_size = current_texture.size[:2]\n",
+            );
+            if let Some(pipeline) = depth {
+                code.push_str(&assign(
+                    12,
+                    "depth_view",
+                    Expr::call(
+                        "create_attachment",
+                        vec![
+                            ("", Expr::raw("device")),
+                            ("", Expr::raw("attachment_size")),
+                            ("", texture_format(&pipeline.depth_format, "Depth24Plus")),
+                            ("", Expr::Raw(sample_count.to_string())),
+                            ("", string("Depth Texture")),
+                        ],
+                    ),
+                ));
+            }
+            if sample_count > 1 {
+                code.push_str(&assign(
+                    12,
+                    "msaa_view",
+                    Expr::call(
+                        "create_attachment",
+                        vec![
+                            ("", Expr::raw("device")),
+                            ("", Expr::raw("attachment_size")),
+                            ("", Expr::raw("surface_format")),
+                            ("", Expr::Raw(sample_count.to_string())),
+                            ("", string("Multisampled Color Texture")),
+                        ],
+                    ),
+                ));
+            }
+        }
+        code.push_str("        command_encoder = device.create_command_encoder()\n\n");
+
+        // Dispatch the compute pipeline before drawing
+        if CodeGenerator::compute_pipeline_state(state).is_some() {
+            let [x, y, z] = self.config.workgroups.unwrap_or([1, 1, 1]);
+            code.push_str(
+                "        compute_pass = command_encoder.begin_compute_pass()\n        \
+                compute_pass.set_pipeline(compute_pipeline)\n",
+            );
+            if has_bind_group {
+                code.push_str("        compute_pass.set_bind_group(0, bind_group)\n");
+            }
+            code.push_str(&format!(
+                "        compute_pass.dispatch_workgroups({}, {}, {})\n        \
+                compute_pass.end()\n\n",
+                x, y, z
+            ));
+        }
+
+        // The pass always clears the window, and draws if there's a pipeline
+        let [r, g, b, a] = self.config.clear_color;
+        let multisampled = sample_count > 1;
+        let mut pass_args = vec![(
+            "color_attachments",
+            Expr::List(vec![Expr::Dict(vec![
+                (
+                    "view",
+                    Expr::raw(if multisampled { "msaa_view" } else { "view" }),
+                ),
+                (
+                    "resolve_target",
+                    Expr::raw(if multisampled { "view" } else { "None" }),
+                ),
+                (
+                    "clear_value",
+                    Expr::Raw(format!("({:.2}, {:.2}, {:.2}, {:.2})", r, g, b, a)),
+                ),
+                ("load_op", Expr::raw("wgpu.LoadOp.clear")),
+                (
+                    "store_op",
+                    Expr::raw(if multisampled {
+                        "wgpu.StoreOp.discard"
+                    } else {
+                        "wgpu.StoreOp.store"
+                    }),
+                ),
+            ])]),
+        )];
+        if let Some(pipeline) = depth {
+            let mut attachment = vec![
+                ("view", Expr::raw("depth_view")),
+                ("depth_clear_value", Expr::raw("1.0")),
+                ("depth_load_op", Expr::raw("wgpu.LoadOp.clear")),
+                ("depth_store_op", Expr::raw("wgpu.StoreOp.store")),
+            ];
+            if pipeline.depth_format.contains("Stencil") {
+                attachment.push(("stencil_load_op", Expr::raw("wgpu.LoadOp.clear")));
+                attachment.push(("stencil_store_op", Expr::raw("wgpu.StoreOp.store")));
+            }
+            pass_args.push(("depth_stencil_attachment", Expr::Dict(attachment)));
+        }
+        code.push_str(&assign(
+            8,
+            "render_pass",
+            Expr::call("command_encoder.begin_render_pass", pass_args),
+        ));
+        if render_pipeline.is_some() {
+            code.push_str("        render_pass.set_pipeline(render_pipeline)\n");
+            if has_bind_group {
+                code.push_str("        render_pass.set_bind_group(0, bind_group)\n");
+            }
+            code.push_str("        render_pass.draw(3, 1, 0, 0)\n");
+        }
+        code.push_str(
+            "        render_pass.end()\n        \
+            device.queue.submit([command_encoder.finish()])\n        \
+            # Draw again on the next frame\n        \
+            canvas.request_draw()\n\n",
+        );
+        code
+    }
+
+    /// README.md with the setup steps
+    fn readme(&self, compute: bool) -> String {
+        let description = if compute {
+            "Standalone WebGPU compute script generated from wgpu_playground. It fills\n\
+            a storage buffer with 0, 1, 2, ..., runs the compute shader on it with\n\
+            [wgpu-py](https://github.com/pygfx/wgpu-py) and prints the first values of\n\
+            the result. No window is opened."
+        } else {
+            "Standalone WebGPU script generated from wgpu_playground. It recreates the\n\
+            playground setup with [wgpu-py](https://github.com/pygfx/wgpu-py) and renders\n\
+            it into a window opened with rendercanvas."
+        };
+        format!(
+            "# {}\n\
+            \n\
+            {}\n\
+            \n\
+            ## Running\n\
+            \n\
+            ```bash\n\
+            python3 -m venv .venv\n\
+            . .venv/bin/activate\n\
+            pip install -r requirements.txt\n\
+            python main.py\n\
+            ```\n\
+            \n\
+            ## Requirements\n\
+            \n\
+            - Python 3.9+\n\
+            - A GPU with WebGPU support (Vulkan, Metal, or DirectX 12)\n\
+            \n\
+            ---\n\
+            \n\
+            Generated by wgpu_playground\n",
+            self.config.project_name, description
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code_generator::ExportTarget;
+
+    #[test]
+    fn test_texture_format_members() {
+        for (name, expected) in [
+            ("Rgba8UnormSrgb", "rgba8unorm_srgb"),
+            ("Bgra8Unorm", "bgra8unorm"),
+            ("Rgb10a2Unorm", "rgb10a2unorm"),
+            ("Depth24PlusStencil8", "depth24plus_stencil8"),
+            ("Depth32FloatStencil8", "depth32float_stencil8"),
+            ("Stencil8", "stencil8"),
+            ("Bc1RgbaUnormSrgb", "bc1_rgba_unorm_srgb"),
+            ("Bc6hRgbUfloat", "bc6h_rgb_ufloat"),
+            ("Etc2Rgb8UnormSrgb", "etc2_rgb8unorm_srgb"),
+            ("EacRg11Snorm", "eac_rg11snorm"),
+            ("not a format", "rgba8unorm"),
+        ] {
+            assert_eq!(
+                texture_format(name, "Rgba8Unorm").one_line(),
+                format!("wgpu.TextureFormat.{}", expected),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_enum_members() {
+        assert_eq!(
+            enum_member("BlendFactor", "OneMinusSrcAlpha", "One").one_line(),
+            "wgpu.BlendFactor.one_minus_src_alpha"
+        );
+        assert_eq!(
+            enum_member("FrontFace", "Ccw", "Ccw").one_line(),
+            "wgpu.FrontFace.ccw"
+        );
+    }
+
+    #[test]
+    fn test_long_expressions_wrap() {
+        let short = Expr::call("f", vec![("", Expr::raw("a")), ("b", string("c"))]);
+        assert_eq!(statement(4, short), "    f(a, b=\"c\")\n");
+
+        let long = Expr::call(
+            "device.create_buffer",
+            vec![
+                ("label", string("a fairly long buffer label")),
+                ("size", Expr::raw("256")),
+                ("usage", Expr::raw("wgpu.BufferUsage.UNIFORM")),
+            ],
+        );
+        assert_eq!(
+            assign(4, "buffer", long),
+            "    buffer = device.create_buffer(\n        \
+                label=\"a fairly long buffer label\",\n        \
+                size=256,\n        \
+                usage=wgpu.BufferUsage.UNIFORM,\n    \
+            )\n"
+        );
+    }
+
+    #[test]
+    fn test_python_project_without_state_draws_triangle() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_python_triangle");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let config = CodeGenConfig::new("python_triangle".to_string())
+            .with_canvas_size(640, 480)
+            .with_target(ExportTarget::Python);
+        CodeGenerator::new(config).generate(&temp_dir).unwrap();
+
+        assert!(temp_dir.join("requirements.txt").exists());
+        assert!(temp_dir.join("README.md").exists());
+        assert!(!temp_dir.join("Cargo.toml").exists());
+
+        let main_py = std::fs::read_to_string(temp_dir.join("main.py")).unwrap();
+        assert!(main_py.contains("from rendercanvas.auto import RenderCanvas, loop"));
+        assert!(main_py.contains("RenderCanvas(size=(640, 480), title=\"python_triangle\")"));
+        assert!(main_py.contains("fn vs_main(@builtin(vertex_index) index: u32)"));
+        assert!(main_py.contains("\"entry_point\": \"vs_main\""));
+        assert!(main_py.contains("render_pass.draw(3, 1, 0, 0)"));
+        assert!(!main_py.contains("set_bind_group"));
+
+        std::fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_python_compute_project() {
+        let temp_dir = std::env::temp_dir().join("wgpu_test_python_compute");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let config = CodeGenConfig::new("python_compute".to_string())
+            .with_example_type(ExampleType::Compute)
+            .with_target(ExportTarget::Python);
+        CodeGenerator::new(config).generate(&temp_dir).unwrap();
+
+        let requirements = std::fs::read_to_string(temp_dir.join("requirements.txt")).unwrap();
+        assert_eq!(requirements, "wgpu>=0.22\n");
+        let main_py = std::fs::read_to_string(temp_dir.join("main.py")).unwrap();
+        // The default shader doubles f32 values
+        assert!(main_py.contains("array(\"f\", range(BUFFER_SIZE // 4))"));
+        assert!(main_py.contains("compute_pipeline.get_bind_group_layout(0)"));
+        assert!(main_py.contains("WORKGROUPS = (1, 1, 1)"));
+        assert!(main_py.contains("read_buffer(buffer).cast(\"f\")"));
+
+        std::fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
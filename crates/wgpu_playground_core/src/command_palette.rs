@@ -0,0 +1,426 @@
+//! Command palette for running any playground action by name
+//!
+//! Panels describe what they offer as [`Command`]s and register them into a
+//! [`CommandRegistry`]. The palette, opened with Ctrl+P by default, searches
+//! the registry as the user types and hands the chosen command's
+//! [`CommandKind`] back to the GUI, which carries it out the same way as the
+//! corresponding button or shortcut.
+
+use crate::i18n::tr;
+use crate::keybindings::Action;
+
+/// How many recently run commands are listed first when the query is empty
+const RECENT_LIMIT: usize = 8;
+
+/// What running a command does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandKind {
+    /// Run an action that also has a keyboard shortcut
+    Action(Action),
+    /// Switch to a panel, named by the message key of its sidebar entry
+    OpenPanel(&'static str),
+    /// Apply the configuration preset with this id
+    ApplyPreset(&'static str),
+    /// Select the example with this id and start its preview
+    LoadExample(&'static str),
+}
+
+impl CommandKind {
+    /// Category the palette shows next to the command
+    pub fn category(&self) -> CommandCategory {
+        match self {
+            CommandKind::Action(_) => CommandCategory::Action,
+            CommandKind::OpenPanel(_) => CommandCategory::Panel,
+            CommandKind::ApplyPreset(_) => CommandCategory::Preset,
+            CommandKind::LoadExample(_) => CommandCategory::Example,
+        }
+    }
+}
+
+/// Group of commands, shown next to each match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandCategory {
+    Action,
+    Panel,
+    Preset,
+    Example,
+}
+
+impl CommandCategory {
+    /// Display name in the current language
+    pub fn name(&self) -> String {
+        tr(match self {
+            CommandCategory::Action => "command-palette-category-action",
+            CommandCategory::Panel => "command-palette-category-panel",
+            CommandCategory::Preset => "command-palette-category-preset",
+            CommandCategory::Example => "command-palette-category-example",
+        })
+    }
+}
+
+/// An entry of the command palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    pub kind: CommandKind,
+    /// Text the palette shows and searches first
+    pub title: String,
+    /// Further words the command is found by, such as tags or a description
+    pub keywords: Vec<String>,
+    /// Label of the keyboard shortcut running the same command
+    pub shortcut: Option<String>,
+}
+
+impl Command {
+    pub fn new(kind: CommandKind, title: impl Into<String>) -> Self {
+        Self {
+            kind,
+            title: title.into(),
+            keywords: Vec::new(),
+            shortcut: None,
+        }
+    }
+
+    /// Add words the command is also found by
+    pub fn with_keywords<S: Into<String>>(mut self, keywords: impl IntoIterator<Item = S>) -> Self {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// Show the label of a shortcut next to the command
+    pub fn with_shortcut(mut self, shortcut: Option<String>) -> Self {
+        self.shortcut = shortcut;
+        self
+    }
+
+    /// How well every word of `query` matches the command, `None` if one of
+    /// them doesn't
+    ///
+    /// Matches in the title count fully, matches in keywords half.
+    pub fn score(&self, query: &str) -> Option<u32> {
+        query.split_whitespace().try_fold(0, |total, term| {
+            let title = fuzzy_score(term, &self.title);
+            let keywords = self
+                .keywords
+                .iter()
+                .filter_map(|keyword| fuzzy_score(term, keyword))
+                .max()
+                .map(|score| score / 2);
+            title.max(keywords).map(|score| total + score)
+        })
+    }
+}
+
+/// Score of `query` as a case-insensitive subsequence of `text`, `None` if
+/// its characters don't all appear in order
+///
+/// Runs of consecutive characters, characters at the start of a word and a
+/// match at the very start of `text` score higher, so that typing
+/// abbreviations or prefixes ranks the intended command first.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.chars().flat_map(char::to_lowercase) {
+        let found =
+            (position..text.len()).find(|&i| text[i].to_lowercase().eq(std::iter::once(wanted)))?;
+        score += 1;
+        if previous == Some(found.wrapping_sub(1)) {
+            score += 5;
+        }
+        let word_start = found == 0
+            || !text[found - 1].is_alphanumeric()
+            || (text[found - 1].is_lowercase() && text[found].is_uppercase());
+        if word_start {
+            score += 8;
+        }
+        if found == 0 {
+            score += 10;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+/// All commands the palette can run
+///
+/// Panels add theirs through a `register_commands` method; the GUI builds a
+/// fresh registry each time the palette opens so titles follow the current
+/// language and workspace.
+#[derive(Debug, Clone, Default)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a command; one of the same kind registered earlier is replaced
+    pub fn register(&mut self, command: Command) {
+        match self.commands.iter_mut().find(|c| c.kind == command.kind) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+    }
+
+    /// Registered commands in registration order
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Command of the given kind
+    pub fn get(&self, kind: CommandKind) -> Option<&Command> {
+        self.commands.iter().find(|c| c.kind == kind)
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Commands matching `query`, best match first
+    ///
+    /// Equally good matches keep their registration order; an empty query
+    /// matches every command.
+    pub fn search(&self, query: &str) -> Vec<&Command> {
+        let mut matches: Vec<(u32, &Command)> = self
+            .commands
+            .iter()
+            .filter_map(|command| command.score(query).map(|score| (score, command)))
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(_, command)| command).collect()
+    }
+}
+
+/// Searchable popup listing the registered commands
+#[derive(Default)]
+pub struct CommandPalette {
+    open: bool,
+    /// Set until the first frame the palette is shown, so the click that
+    /// opened it doesn't count as a click elsewhere
+    just_opened: bool,
+    query: String,
+    /// Index of the highlighted match
+    selected: usize,
+    registry: CommandRegistry,
+    /// Commands run from the palette, most recent first
+    recent: Vec<CommandKind>,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the palette with an empty query over the commands of `registry`
+    pub fn open(&mut self, registry: CommandRegistry) {
+        self.open = true;
+        self.just_opened = true;
+        self.query.clear();
+        self.selected = 0;
+        self.registry = registry;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Commands run from the palette, most recent first
+    pub fn recent(&self) -> &[CommandKind] {
+        &self.recent
+    }
+
+    /// Replace the query as if it had been typed
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.selected = 0;
+    }
+
+    /// Commands matching the current query, best match first
+    ///
+    /// Without a query, recently run commands come first.
+    pub fn matches(&self) -> Vec<&Command> {
+        if !self.query.trim().is_empty() {
+            return self.registry.search(&self.query);
+        }
+        let recent = self
+            .recent
+            .iter()
+            .filter_map(|&kind| self.registry.get(kind));
+        let rest = self
+            .registry
+            .commands()
+            .iter()
+            .filter(|command| !self.recent.contains(&command.kind));
+        recent.chain(rest).collect()
+    }
+
+    /// Close the palette and remember `kind` as the most recent command
+    fn choose(&mut self, kind: CommandKind) -> CommandKind {
+        self.open = false;
+        self.recent.retain(|&recent| recent != kind);
+        self.recent.insert(0, kind);
+        self.recent.truncate(RECENT_LIMIT);
+        kind
+    }
+
+    /// Run the highlighted match as if Enter had been pressed
+    pub fn confirm(&mut self) -> Option<CommandKind> {
+        let kind = self
+            .matches()
+            .get(self.selected)
+            .map(|command| command.kind)?;
+        Some(self.choose(kind))
+    }
+
+    /// Show the palette if it is open; returns the command to run
+    ///
+    /// Arrow keys move the highlight, Enter runs the highlighted command and
+    /// Escape or a click elsewhere closes the palette.
+    pub fn ui(&mut self, ctx: &egui::Context) -> Option<CommandKind> {
+        if !self.open {
+            return None;
+        }
+
+        // Taken before the text field sees them, which would otherwise move
+        // its cursor or give up focus
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if escape {
+            self.close();
+            return None;
+        }
+        let count = self.matches().len();
+        let moved = up || down;
+        if down && self.selected + 1 < count {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if enter {
+            return self.confirm();
+        }
+
+        let mut clicked = None;
+        let response = egui::Window::new(tr("command-palette-title"))
+            .id(egui::Id::new("command_palette"))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .show(ctx, |ui| {
+                ui.set_width(520.0);
+                let edit = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text(tr("command-palette-hint"))
+                        .desired_width(f32::INFINITY),
+                );
+                edit.request_focus();
+                if edit.changed() {
+                    self.selected = 0;
+                }
+                ui.separator();
+
+                let matches = self.matches();
+                if matches.is_empty() {
+                    ui.weak(tr("command-palette-no-matches"));
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        for (index, command) in matches.iter().enumerate() {
+                            let selected = index == self.selected;
+                            let row = ui
+                                .horizontal(|ui| {
+                                    let title = ui.selectable_label(selected, &command.title);
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if let Some(shortcut) = &command.shortcut {
+                                                ui.label(egui::RichText::new(shortcut).monospace());
+                                            }
+                                            ui.weak(command.kind.category().name());
+                                        },
+                                    );
+                                    title
+                                })
+                                .inner;
+                            if selected && moved {
+                                row.scroll_to_me(None);
+                            }
+                            if row.clicked() {
+                                clicked = Some(command.kind);
+                            }
+                        }
+                    });
+            });
+
+        if let Some(kind) = clicked {
+            return Some(self.choose(kind));
+        }
+        let clicked_elsewhere = !std::mem::take(&mut self.just_opened)
+            && ctx.input(|i| i.pointer.any_click())
+            && response.is_some_and(|response| !response.response.contains_pointer());
+        if clicked_elsewhere {
+            self.close();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_requires_subsequence() {
+        assert!(fuzzy_score("cmp", "Compile shader").is_some());
+        assert!(fuzzy_score("CS", "compile shader").is_some());
+        assert!(fuzzy_score("shc", "Compile shader").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts() {
+        let prefix = fuzzy_score("tex", "Textures").unwrap();
+        let inner = fuzzy_score("tex", "Vertex Buffers").unwrap();
+        assert!(prefix > inner);
+        let initials = fuzzy_score("rp", "Render Pipeline").unwrap();
+        let scattered = fuzzy_score("rp", "Draw Properties").unwrap();
+        assert!(initials > scattered);
+    }
+
+    #[test]
+    fn test_register_replaces_same_kind() {
+        let mut registry = CommandRegistry::new();
+        registry.register(Command::new(
+            CommandKind::OpenPanel("nav-console"),
+            "Console",
+        ));
+        registry.register(
+            Command::new(CommandKind::OpenPanel("nav-console"), "Console")
+                .with_shortcut(Some("Ctrl+5".to_string())),
+        );
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.commands()[0].shortcut.as_deref(), Some("Ctrl+5"));
+    }
+}
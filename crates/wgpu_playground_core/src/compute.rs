@@ -2,6 +2,8 @@ use std::fmt;
 use wgpu::{ComputePipeline, Device, PipelineLayout};
 
 use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::device_recovery::ResourceRecreate;
+use crate::operation_errors::OperationErrors;
 use crate::shader::ShaderModule;
 
 /// Errors that can occur during compute pipeline operations
@@ -248,6 +250,7 @@ impl ComputePipelineDescriptor {
         &self,
         device: &Device,
     ) -> Result<ComputePipeline, ComputePipelineError> {
+        crate::cpu_scope!("Create compute pipeline");
         self.validate()?;
 
         let tracker = ApiCoverageTracker::global();
@@ -271,7 +274,7 @@ impl ComputePipelineDescriptor {
             module: &shader_module,
             entry_point: self.entry_point.as_deref(),
             compilation_options: Default::default(),
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         Ok(pipeline)
@@ -555,8 +558,17 @@ pub struct ComputePanel {
     execution_time_ms: Option<f64>,
     /// Show shader source
     show_shader_source: bool,
+    /// Path for exporting the output data
+    export_path: String,
+    /// Export result message
+    export_message: Option<String>,
+    /// Device errors from the last GPU run
+    gpu_errors: OperationErrors,
 }
 
+/// Operation name of a GPU compute run in [`OperationErrors`]
+const RUN_COMPUTE: &str = "run_compute";
+
 impl Default for ComputePanel {
     fn default() -> Self {
         Self::new()
@@ -577,9 +589,28 @@ impl ComputePanel {
             workgroup_size_x: 64,
             execution_time_ms: None,
             show_shader_source: false,
+            export_path: "compute_output.csv".to_string(),
+            export_message: None,
+            gpu_errors: OperationErrors::new(),
         }
     }
 
+    /// Export the output data as CSV (one value per row) or raw `f32` binary
+    fn export_output(&self, path: &std::path::Path) -> Result<(), crate::buffer_io::BufferIoError> {
+        use crate::buffer_io::{export_csv, is_csv_path, ColumnType};
+
+        let data: &[u8] = bytemuck::cast_slice(&self.output_data);
+        if is_csv_path(path) {
+            std::fs::write(
+                path,
+                export_csv(data, &[ColumnType::F32], Some(&["value".to_string()]))?,
+            )?;
+        } else {
+            std::fs::write(path, data)?;
+        }
+        Ok(())
+    }
+
     /// Reset input data to default values
     fn reset_input(&mut self) {
         self.input_data = (1..=self.input_size).map(|i| i as f32).collect();
@@ -590,6 +621,16 @@ impl ComputePanel {
         self.execution_time_ms = None;
     }
 
+    /// Run the selected example like its run buttons do: on the GPU where
+    /// that is supported, otherwise as a CPU simulation
+    pub fn run(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.selected_example == ComputeExample::ArrayDouble {
+            self.run_compute_gpu(device, queue);
+        } else {
+            self.run_compute_cpu();
+        }
+    }
+
     /// Run the compute shader (CPU simulation for now, can be extended to GPU)
     fn run_compute_cpu(&mut self) {
         let start = std::time::Instant::now();
@@ -670,8 +711,22 @@ impl ComputePanel {
         self.error_message = None;
     }
 
-    /// Run compute on GPU
+    /// Run compute on GPU, showing device errors next to the run button
     fn run_compute_gpu(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let gpu_errors = self.gpu_errors.clone();
+        gpu_errors.scoped(device, RUN_COMPUTE, || {
+            self.dispatch_compute_gpu(device, queue)
+        });
+        if gpu_errors.has_error(RUN_COMPUTE) {
+            // Results of an invalid dispatch are meaningless
+            self.output_data.clear();
+            self.has_run = false;
+            self.status_message = None;
+            self.error_message = None;
+        }
+    }
+
+    fn dispatch_compute_gpu(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
         use wgpu::util::DeviceExt;
 
         let tracker = ApiCoverageTracker::global();
@@ -749,7 +804,7 @@ impl ComputePanel {
             module: &shader,
             entry_point: Some("main"),
             compilation_options: Default::default(),
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         // Create command encoder and run compute pass
@@ -990,6 +1045,7 @@ impl ComputePanel {
                     }
                 }
             });
+            self.gpu_errors.ui(ui, RUN_COMPUTE);
 
             ui.add_space(10.0);
 
@@ -1023,6 +1079,35 @@ impl ComputePanel {
                                 }
                             });
                         });
+
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.export_path)
+                                .desired_width(200.0)
+                                .hint_text("output.csv or output.bin"),
+                        );
+                        if ui
+                            .button("💾 Export")
+                            .on_hover_text("Save the output as CSV or raw f32 binary")
+                            .clicked()
+                        {
+                            let path = std::path::PathBuf::from(self.export_path.trim());
+                            self.export_message = Some(match self.export_output(&path) {
+                                Ok(()) => format!("✓ Exported to {}", path.display()),
+                                Err(e) => format!("✗ {}", e),
+                            });
+                        }
+                    });
+                    if let Some(msg) = &self.export_message {
+                        ui.colored_label(
+                            if msg.starts_with('✓') {
+                                egui::Color32::GREEN
+                            } else {
+                                egui::Color32::RED
+                            },
+                            msg,
+                        );
+                    }
                 });
             }
 
@@ -1044,6 +1129,13 @@ impl ComputePanel {
     }
 }
 
+impl ResourceRecreate for ComputePanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // Each run creates its resources; only errors from the old device remain
+        self.gpu_errors.clear_all();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
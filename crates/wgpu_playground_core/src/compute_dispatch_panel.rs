@@ -84,6 +84,15 @@ impl ComputeDispatchPanel {
         Ok((workgroups_x, workgroups_y, workgroups_z))
     }
 
+    /// Workgroup counts of a valid direct dispatch, `None` for indirect
+    /// dispatches whose counts are only known on the GPU
+    pub fn workgroups(&self) -> Option<[u32; 3]> {
+        match self.dispatch_type {
+            DispatchType::Direct => self.parse_workgroups().ok().map(|(x, y, z)| [x, y, z]),
+            DispatchType::Indirect => None,
+        }
+    }
+
     /// Validate the current parameters
     fn validate(&mut self) -> bool {
         self.validation_error = None;
@@ -410,6 +419,21 @@ mod tests {
         assert_eq!(panel.dispatch_type, DispatchType::Direct);
     }
 
+    #[test]
+    fn test_workgroups() {
+        let mut panel = ComputeDispatchPanel::new();
+        panel.workgroups_x_input = "16".to_string();
+        panel.workgroups_y_input = "4".to_string();
+        assert_eq!(panel.workgroups(), Some([16, 4, 1]));
+
+        panel.workgroups_z_input = "0".to_string();
+        assert_eq!(panel.workgroups(), None);
+
+        panel.workgroups_z_input = "1".to_string();
+        panel.dispatch_type = DispatchType::Indirect;
+        assert_eq!(panel.workgroups(), None);
+    }
+
     #[test]
     fn test_validate_direct_success() {
         let mut panel = ComputeDispatchPanel::new();
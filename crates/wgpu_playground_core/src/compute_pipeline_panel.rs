@@ -1,4 +1,8 @@
+use crate::capability_snapshot::ProjectDescriptor;
 use crate::compute::ComputePipelineDescriptor;
+use crate::device_recovery::ResourceRecreate;
+use crate::i18n::{tr, tr_args};
+use crate::operation_errors::OperationErrors;
 use crate::shader::ShaderModule;
 use crate::tooltip::compute;
 
@@ -22,8 +26,13 @@ pub struct ComputePipelinePanel {
     pub success_message: Option<String>,
     /// Compiled shader module (cached)
     pub cached_shader: Option<ShaderModule>,
+    /// Device errors from creating the pipeline
+    gpu_errors: OperationErrors,
 }
 
+/// Operation name of pipeline creation in [`OperationErrors`]
+const CREATE_PIPELINE: &str = "create_pipeline";
+
 impl Default for ComputePipelinePanel {
     fn default() -> Self {
         Self::new()
@@ -44,6 +53,7 @@ impl ComputePipelinePanel {
             validation_error: None,
             success_message: None,
             cached_shader: None,
+            gpu_errors: OperationErrors::new(),
         }
     }
 
@@ -78,7 +88,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
                     self.cached_shader = Some(shader);
                 }
                 Err(e) => {
-                    return Err(format!("Shader compilation error: {}", e));
+                    return Err(tr_args("compute-pipeline-shader-error", &[("error", &e)]));
                 }
             }
         }
@@ -104,6 +114,23 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         Ok(())
     }
 
+    /// The workgroup size of the configured entry point, for checks against
+    /// a virtual device
+    ///
+    /// `None` while the shader does not parse or has no such compute entry
+    /// point. Overridden dimensions count as 1.
+    pub fn project_descriptor(&self) -> Option<ProjectDescriptor> {
+        let module = naga::front::wgsl::parse_str(&self.shader_source).ok()?;
+        let entry = module.entry_points.iter().find(|ep| {
+            ep.stage == naga::ShaderStage::Compute
+                && (self.entry_point_input.is_empty() || ep.name == self.entry_point_input)
+        })?;
+        Some(ProjectDescriptor::Workgroup {
+            entry_point: entry.name.clone(),
+            size: entry.workgroup_size,
+        })
+    }
+
     /// Validate the current configuration
     pub fn validate(&mut self) -> bool {
         match self.update_descriptor() {
@@ -133,21 +160,29 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             return None;
         }
 
-        match self.descriptor.create_pipeline(device) {
+        let result = self.gpu_errors.scoped(device, CREATE_PIPELINE, || {
+            self.descriptor.create_pipeline(device)
+        });
+        match result {
+            // The device rejected the pipeline; the error is shown inline
+            Ok(_) if self.gpu_errors.has_error(CREATE_PIPELINE) => {
+                self.success_message = None;
+                None
+            }
             Ok(pipeline) => {
-                self.success_message = Some(format!(
-                    "✓ Compute pipeline created successfully: '{}'",
-                    if self.label_input.trim().is_empty() {
-                        "unlabeled"
-                    } else {
-                        self.label_input.as_str()
-                    }
-                ));
+                let label = if self.label_input.trim().is_empty() {
+                    tr("compute-pipeline-unlabeled")
+                } else {
+                    self.label_input.clone()
+                };
+                self.success_message =
+                    Some(tr_args("compute-pipeline-created", &[("label", &label)]));
                 self.validation_error = None;
                 Some(pipeline)
             }
             Err(e) => {
-                self.validation_error = Some(format!("Failed to create pipeline: {}", e));
+                self.validation_error =
+                    Some(tr_args("compute-pipeline-create-failed", &[("error", &e)]));
                 self.success_message = None;
                 None
             }
@@ -162,25 +197,25 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     /// Render the compute pipeline configuration UI with optional device for pipeline creation
     pub fn ui_with_device(&mut self, ui: &mut egui::Ui, device: Option<&wgpu::Device>) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("⚙️ Compute Pipeline Configuration");
-            ui.label("Configure and create compute pipelines for GPU compute operations.");
+            ui.heading(tr("compute-pipeline-heading"));
+            ui.label(tr("compute-pipeline-intro"));
             ui.add_space(10.0);
 
             // Pipeline Properties
             ui.group(|ui| {
-                ui.heading("Pipeline Properties");
+                ui.heading(tr("compute-pipeline-properties"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("pipeline_properties")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Pipeline Label:");
+                        ui.label(tr("compute-pipeline-label"));
                         ui.text_edit_singleline(&mut self.label_input)
-                            .on_hover_text("Optional label for debugging");
+                            .on_hover_text(tr("compute-pipeline-label-hover"));
                         ui.end_row();
 
-                        ui.label("Entry Point:");
+                        ui.label(tr("compute-pipeline-entry-point"));
                         compute::ENTRY_POINT
                             .apply(ui.text_edit_singleline(&mut self.entry_point_input));
                         ui.end_row();
@@ -191,22 +226,22 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
             // Shader Module Configuration
             ui.group(|ui| {
-                ui.heading("Shader Module");
-                ui.label("Configure the compute shader for this pipeline.");
+                ui.heading(tr("compute-pipeline-shader-heading"));
+                ui.label(tr("compute-pipeline-shader-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("shader_module")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Shader Label:");
+                        ui.label(tr("compute-pipeline-shader-label"));
                         ui.text_edit_singleline(&mut self.shader_label)
-                            .on_hover_text("Optional label for the shader module");
+                            .on_hover_text(tr("compute-pipeline-shader-label-hover"));
                         ui.end_row();
                     });
 
                 ui.add_space(5.0);
-                ui.label("Shader Source (WGSL):");
+                ui.label(tr("compute-pipeline-shader-source"));
                 ui.add_space(2.0);
 
                 // Shader code editor
@@ -222,16 +257,16 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
                 // Shader templates
                 ui.horizontal(|ui| {
-                    ui.label("Templates:");
-                    if ui.button("Simple Compute").clicked() {
+                    ui.label(tr("compute-pipeline-templates"));
+                    if ui.button(tr("compute-pipeline-template-simple")).clicked() {
                         self.shader_source = Self::default_compute_shader();
                         self.cached_shader = None;
                     }
-                    if ui.button("Storage Buffer").clicked() {
+                    if ui.button(tr("compute-pipeline-template-storage")).clicked() {
                         self.shader_source = Self::storage_buffer_shader();
                         self.cached_shader = None;
                     }
-                    if ui.button("Matrix Multiply").clicked() {
+                    if ui.button(tr("compute-pipeline-template-matrix")).clicked() {
                         self.shader_source = Self::matrix_multiply_shader();
                         self.cached_shader = None;
                     }
@@ -242,20 +277,22 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
             // Pipeline Layout Configuration
             ui.group(|ui| {
-                ui.heading("Pipeline Layout");
-                ui.label("Configure how bind groups are organized in the pipeline.");
+                ui.heading(tr("compute-pipeline-layout-heading"));
+                ui.label(tr("compute-pipeline-layout-intro"));
                 ui.add_space(5.0);
 
-                compute::PIPELINE_LAYOUT
-                    .apply(ui.checkbox(&mut self.use_auto_layout, "Use Auto-Generated Layout"));
+                compute::PIPELINE_LAYOUT.apply(ui.checkbox(
+                    &mut self.use_auto_layout,
+                    tr("compute-pipeline-layout-auto"),
+                ));
 
                 if !self.use_auto_layout {
                     ui.add_space(5.0);
                     ui.colored_label(
                         egui::Color32::YELLOW,
-                        "⚠️ Manual layout configuration not yet implemented",
+                        tr("compute-pipeline-layout-manual-missing"),
                     );
-                    ui.label("For now, auto-generated layouts are used.");
+                    ui.label(tr("compute-pipeline-layout-manual-fallback"));
                 }
             });
 
@@ -266,7 +303,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             ui.add_space(5.0);
 
             ui.horizontal(|ui| {
-                if ui.button("Validate Configuration").clicked() {
+                if ui.button(tr("compute-pipeline-validate")).clicked() {
                     self.validate();
                 }
 
@@ -275,13 +312,13 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
                 // Create Pipeline button - enabled when device is available
                 let can_create = device.is_some();
                 if ui
-                    .add_enabled(can_create, egui::Button::new("✨ Create Pipeline"))
+                    .add_enabled(can_create, egui::Button::new(tr("compute-pipeline-create")))
                     .on_hover_text(if can_create {
-                        "Create the compute pipeline with current configuration"
+                        tr("compute-pipeline-create-hover")
                     } else {
-                        "Pipeline creation requires GPU device access"
+                        tr("compute-pipeline-create-needs-device")
                     })
-                    .on_disabled_hover_text("Pipeline creation requires GPU device access")
+                    .on_disabled_hover_text(tr("compute-pipeline-create-needs-device"))
                     .clicked()
                 {
                     if let Some(dev) = device {
@@ -291,13 +328,17 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
                     }
                 }
             });
+            self.gpu_errors.ui(ui, CREATE_PIPELINE);
 
             ui.add_space(5.0);
 
             // Display validation errors
             if let Some(error) = &self.validation_error {
                 ui.add_space(5.0);
-                ui.colored_label(egui::Color32::RED, format!("❌ Error: {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("compute-pipeline-error", &[("error", error)]),
+                );
             }
 
             // Display success messages
@@ -311,25 +352,39 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
             // Information Section
             ui.group(|ui| {
-                ui.heading("ℹ️ Compute Pipeline Information");
+                ui.heading(tr("compute-pipeline-info-heading"));
                 ui.add_space(5.0);
 
-                ui.label("A compute pipeline consists of:");
-                ui.label("• Shader Module: Contains the compute shader code (WGSL)");
-                ui.label("• Entry Point: The function name to execute (e.g., 'main')");
-                ui.label(
-                    "• Pipeline Layout: Defines bind group organization (auto-generated or manual)",
-                );
+                ui.label(tr("compute-pipeline-info-parts"));
+                ui.label(tr("compute-pipeline-info-shader"));
+                ui.label(tr("compute-pipeline-info-entry-point"));
+                ui.label(tr("compute-pipeline-info-layout"));
 
                 ui.add_space(5.0);
-                ui.label("Compute shaders must have:");
-                ui.label("• @compute attribute on the entry point function");
-                ui.label("• @workgroup_size attribute specifying execution dimensions");
-                ui.label("• Example: @compute @workgroup_size(64, 1, 1)");
+                ui.label(tr("compute-pipeline-info-requirements"));
+                ui.label(tr("compute-pipeline-info-compute"));
+                ui.label(tr("compute-pipeline-info-workgroup-size"));
+                ui.label(tr("compute-pipeline-info-example"));
             });
         });
     }
 
+    /// Export the current state to a serializable format
+    pub fn export_state(&self) -> crate::state::ComputePipelinePanelState {
+        crate::state::ComputePipelinePanelState {
+            label: self.label_input.clone(),
+            entry_point: self.entry_point_input.clone(),
+        }
+    }
+
+    /// Import state from a serializable format
+    pub fn import_state(&mut self, state: &crate::state::ComputePipelinePanelState) {
+        self.label_input = state.label.clone();
+        self.entry_point_input = state.entry_point.clone();
+        self.validation_error = None;
+        self.success_message = None;
+    }
+
     /// Get storage buffer shader template
     pub fn storage_buffer_shader() -> String {
         r#"// Compute shader with storage buffer
@@ -385,6 +440,13 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 }
 
+impl ResourceRecreate for ComputePipelinePanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // Pipelines are created on demand; only errors from the old device remain
+        self.gpu_errors.clear_all();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,4 +548,19 @@ mod tests {
         panel.label_input = "my_pipeline".to_string();
         assert!(panel.update_descriptor().is_ok());
     }
+
+    #[test]
+    fn test_project_descriptor_reads_workgroup_size() {
+        let mut panel = ComputePipelinePanel::new();
+        assert_eq!(
+            panel.project_descriptor(),
+            Some(ProjectDescriptor::Workgroup {
+                entry_point: "main".to_string(),
+                size: [64, 1, 1],
+            })
+        );
+
+        panel.entry_point_input = "missing".to_string();
+        assert_eq!(panel.project_descriptor(), None);
+    }
 }
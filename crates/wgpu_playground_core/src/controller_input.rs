@@ -0,0 +1,1145 @@
+//! Gamepad and MIDI controllers mapped to shader uniforms
+//!
+//! Gamepad axes and buttons (read with gilrs when built with the `gamepad`
+//! feature) and MIDI control change messages (read with midir when built
+//! with the `midi` feature) arrive as [`InputEvent`]s. Each
+//! [`InputMapping`] binds one control to one component of a named target,
+//! a uniform field or a parameter of the panel, and turns the control's
+//! value into a value between its `min` and `max`:
+//!
+//! - absolute: the control's position picks the value, a centered stick
+//!   the middle of the range
+//! - toggle: each press of a button flips between `min` and `max`
+//! - relative: holding a stick over moves the value, for parameters that
+//!   should stay put when the stick is let go
+//!
+//! A mapping only writes its target once its control has moved, so the
+//! widgets keep working until then. MIDI learn binds a mapping to the next
+//! control that moves.
+
+use std::collections::HashMap;
+
+use crate::uniform_tweaker::{UniformBlock, UniformTweaker};
+
+/// Stick deflection ignored around the center
+const DEAD_ZONE: f32 = 0.1;
+
+/// Fraction of its range a relative mapping moves per second at full
+/// deflection
+const RELATIVE_RATE: f32 = 0.5;
+
+/// A gamepad axis, reported from -1 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    /// Left analog trigger on pads that report it as an axis
+    LeftZ,
+    /// Right analog trigger on pads that report it as an axis
+    RightZ,
+    DPadX,
+    DPadY,
+}
+
+impl GamepadAxis {
+    pub const ALL: [GamepadAxis; 8] = [
+        GamepadAxis::LeftStickX,
+        GamepadAxis::LeftStickY,
+        GamepadAxis::RightStickX,
+        GamepadAxis::RightStickY,
+        GamepadAxis::LeftZ,
+        GamepadAxis::RightZ,
+        GamepadAxis::DPadX,
+        GamepadAxis::DPadY,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GamepadAxis::LeftStickX => "Left stick X",
+            GamepadAxis::LeftStickY => "Left stick Y",
+            GamepadAxis::RightStickX => "Right stick X",
+            GamepadAxis::RightStickY => "Right stick Y",
+            GamepadAxis::LeftZ => "Left Z",
+            GamepadAxis::RightZ => "Right Z",
+            GamepadAxis::DPadX => "D-pad X",
+            GamepadAxis::DPadY => "D-pad Y",
+        }
+    }
+}
+
+/// A gamepad button, reported from 0 to 1, in between for analog triggers
+///
+/// The face buttons are named by position, `South` is A on an Xbox pad
+/// and Cross on a PlayStation pad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub const ALL: [GamepadButton; 17] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::North,
+        GamepadButton::West,
+        GamepadButton::LeftBumper,
+        GamepadButton::RightBumper,
+        GamepadButton::LeftTrigger,
+        GamepadButton::RightTrigger,
+        GamepadButton::Select,
+        GamepadButton::Start,
+        GamepadButton::Mode,
+        GamepadButton::LeftThumb,
+        GamepadButton::RightThumb,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GamepadButton::South => "South (A / Cross)",
+            GamepadButton::East => "East (B / Circle)",
+            GamepadButton::North => "North (Y / Triangle)",
+            GamepadButton::West => "West (X / Square)",
+            GamepadButton::LeftBumper => "Left bumper",
+            GamepadButton::RightBumper => "Right bumper",
+            GamepadButton::LeftTrigger => "Left trigger",
+            GamepadButton::RightTrigger => "Right trigger",
+            GamepadButton::Select => "Select",
+            GamepadButton::Start => "Start",
+            GamepadButton::Mode => "Mode",
+            GamepadButton::LeftThumb => "Left stick press",
+            GamepadButton::RightThumb => "Right stick press",
+            GamepadButton::DPadUp => "D-pad up",
+            GamepadButton::DPadDown => "D-pad down",
+            GamepadButton::DPadLeft => "D-pad left",
+            GamepadButton::DPadRight => "D-pad right",
+        }
+    }
+}
+
+/// A physical control of any connected gamepad or MIDI device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputControl {
+    Axis(GamepadAxis),
+    Button(GamepadButton),
+    /// MIDI control change, reported from 0 to 1
+    MidiCc {
+        /// Channel from 0 to 15, shown from 1
+        channel: u8,
+        controller: u8,
+    },
+}
+
+impl InputControl {
+    pub fn label(&self) -> String {
+        match self {
+            InputControl::Axis(axis) => format!("🎮 {}", axis.name()),
+            InputControl::Button(button) => format!("🎮 {}", button.name()),
+            InputControl::MidiCc {
+                channel,
+                controller,
+            } => format!("🎹 MIDI ch {} CC {}", channel + 1, controller),
+        }
+    }
+
+    /// Value from -1 to 1 the control moves a relative mapping by
+    fn signed(&self, raw: f32) -> f32 {
+        match self {
+            InputControl::Axis(_) => dead_zone(raw),
+            // Knobs are centered like a stick
+            InputControl::MidiCc { .. } => dead_zone(raw * 2.0 - 1.0),
+            InputControl::Button(_) => raw,
+        }
+    }
+
+    /// Position from 0 to 1 an absolute mapping picks its value by
+    fn position(&self, raw: f32) -> f32 {
+        match self {
+            InputControl::Axis(_) => (dead_zone(raw) + 1.0) / 2.0,
+            InputControl::Button(_) | InputControl::MidiCc { .. } => raw.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Rescale a stick value so the dead zone reads 0 and the edges still 1
+fn dead_zone(value: f32) -> f32 {
+    let magnitude = ((value.abs() - DEAD_ZONE) / (1.0 - DEAD_ZONE)).clamp(0.0, 1.0);
+    magnitude.copysign(value)
+}
+
+/// A control moved to a new value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputEvent {
+    pub control: InputControl,
+    pub value: f32,
+}
+
+/// Read a MIDI control change message; other messages give `None`
+pub fn parse_midi(message: &[u8]) -> Option<InputEvent> {
+    let [status, controller, value, ..] = *message else {
+        return None;
+    };
+    if status & 0xF0 != 0xB0 {
+        return None;
+    }
+    Some(InputEvent {
+        control: InputControl::MidiCc {
+            channel: status & 0x0F,
+            controller: controller & 0x7F,
+        },
+        value: (value & 0x7F) as f32 / 127.0,
+    })
+}
+
+/// How a mapping turns its control's value into the target's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingMode {
+    /// The control's position picks the value
+    Absolute,
+    /// Each press flips between the ends of the range
+    Toggle,
+    /// The control moves the value while held over
+    Relative,
+}
+
+impl MappingMode {
+    pub const ALL: [MappingMode; 3] = [
+        MappingMode::Absolute,
+        MappingMode::Toggle,
+        MappingMode::Relative,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MappingMode::Absolute => "Absolute",
+            MappingMode::Toggle => "Toggle",
+            MappingMode::Relative => "Relative",
+        }
+    }
+}
+
+/// A value a mapping can drive, like a uniform field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingTarget {
+    pub name: String,
+    /// Number of components, 1 to 4
+    pub components: usize,
+}
+
+/// The fields of the uniform blocks `show` accepts as mapping targets
+pub fn uniform_targets(
+    uniforms: &UniformTweaker,
+    show: impl Fn(&UniformBlock) -> bool,
+) -> Vec<MappingTarget> {
+    uniforms
+        .blocks()
+        .iter()
+        .filter(|block| show(block))
+        .flat_map(|block| &block.fields)
+        .map(|field| MappingTarget {
+            name: field.name.clone(),
+            components: field.components,
+        })
+        .collect()
+}
+
+/// A control bound to one component of a target
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputMapping {
+    /// `None` until a control is picked or learned
+    pub control: Option<InputControl>,
+    /// Name of the target
+    pub target: String,
+    /// Component of the target written, 0 for `x`
+    pub component: usize,
+    /// Value at the low end of the control
+    pub min: f32,
+    /// Value at the high end of the control
+    pub max: f32,
+    pub mode: MappingMode,
+    /// `None` until the control moves
+    value: Option<f32>,
+    /// Whether a toggle mapping is at `max`
+    toggled: bool,
+    /// Whether the button of a toggle mapping is down
+    held: bool,
+}
+
+impl InputMapping {
+    /// Map `control` over 0..1 of the first component of `target`
+    pub fn new(control: Option<InputControl>, target: impl Into<String>) -> Self {
+        Self {
+            control,
+            target: target.into(),
+            component: 0,
+            min: 0.0,
+            max: 1.0,
+            mode: MappingMode::Absolute,
+            value: None,
+            toggled: false,
+            held: false,
+        }
+    }
+
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: MappingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_component(mut self, component: usize) -> Self {
+        self.component = component;
+        self
+    }
+
+    /// Value written to the target, once the control has moved
+    pub fn value(&self) -> Option<f32> {
+        self.value
+    }
+
+    /// Forget the value, leaving the target to its widget again
+    pub fn reset(&mut self) {
+        self.value = None;
+        self.toggled = false;
+        self.held = false;
+    }
+
+    fn input(&mut self, control: InputControl, raw: f32) {
+        match self.mode {
+            MappingMode::Absolute => {
+                let t = control.position(raw);
+                self.value = Some(self.min + t * (self.max - self.min));
+            }
+            MappingMode::Toggle => {
+                let pressed = control.position(raw) > 0.5;
+                if pressed && !self.held {
+                    self.toggled = !self.toggled;
+                }
+                self.held = pressed;
+                self.value = Some(if self.toggled { self.max } else { self.min });
+            }
+            MappingMode::Relative => {
+                self.value.get_or_insert((self.min + self.max) / 2.0);
+            }
+        }
+    }
+
+    fn advance(&mut self, control: InputControl, raw: f32, dt: f32) {
+        let Some(value) = &mut self.value else {
+            return;
+        };
+        if self.mode != MappingMode::Relative {
+            return;
+        }
+        let (low, high) = (self.min.min(self.max), self.min.max(self.max));
+        let step = control.signed(raw) * (self.max - self.min) * RELATIVE_RATE * dt;
+        *value = (*value + step).clamp(low, high);
+    }
+}
+
+/// The mappings and the last value of every control that moved
+#[derive(Debug, Clone, Default)]
+pub struct InputMapper {
+    mappings: Vec<InputMapping>,
+    raw: HashMap<InputControl, f32>,
+    /// Mapping bound to the next control that moves
+    learning: Option<usize>,
+}
+
+impl InputMapper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mappings(&self) -> &[InputMapping] {
+        &self.mappings
+    }
+
+    pub fn add(&mut self, mapping: InputMapping) {
+        self.mappings.push(mapping);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.mappings.len() {
+            self.mappings.remove(index);
+        }
+        self.learning = match self.learning {
+            Some(learning) if learning == index => None,
+            Some(learning) if learning > index => Some(learning - 1),
+            learning => learning,
+        };
+    }
+
+    /// Bind mapping `index` to the next control that moves
+    pub fn learn(&mut self, index: usize) {
+        self.learning = (index < self.mappings.len()).then_some(index);
+    }
+
+    /// Mapping waiting for a control to move
+    pub fn learning(&self) -> Option<usize> {
+        self.learning
+    }
+
+    /// Last value of a control, if it moved
+    pub fn raw(&self, control: InputControl) -> Option<f32> {
+        self.raw.get(&control).copied()
+    }
+
+    /// Controls that moved, in no particular order
+    pub fn seen_controls(&self) -> impl Iterator<Item = InputControl> + '_ {
+        self.raw.keys().copied()
+    }
+
+    pub fn handle(&mut self, event: InputEvent) {
+        let InputEvent { control, value } = event;
+        self.raw.insert(control, value);
+
+        if let Some(index) = self.learning {
+            // Sticks and triggers rest near 0, a small touch shouldn't count
+            let moved = match control {
+                InputControl::Axis(_) | InputControl::Button(_) => value.abs() > 0.5,
+                InputControl::MidiCc { .. } => true,
+            };
+            if moved {
+                let mapping = &mut self.mappings[index];
+                mapping.control = Some(control);
+                mapping.reset();
+                self.learning = None;
+            }
+        }
+
+        for mapping in &mut self.mappings {
+            if mapping.control == Some(control) {
+                mapping.input(control, value);
+            }
+        }
+    }
+
+    /// Move the relative mappings by the controls held over
+    pub fn update(&mut self, dt: f32) {
+        for mapping in &mut self.mappings {
+            let Some(control) = mapping.control else {
+                continue;
+            };
+            if let Some(&raw) = self.raw.get(&control) {
+                mapping.advance(control, raw, dt);
+            }
+        }
+    }
+
+    /// Target, component and value of every mapping whose control moved
+    pub fn outputs(&self) -> impl Iterator<Item = (&str, usize, f32)> + '_ {
+        self.mappings.iter().filter_map(|mapping| {
+            let value = mapping.value?;
+            Some((mapping.target.as_str(), mapping.component, value))
+        })
+    }
+
+    /// Write the outputs into the uniform fields they name
+    pub fn apply(&self, uniforms: &mut UniformTweaker) {
+        for (target, component, value) in self.outputs() {
+            uniforms.set_component(target, component, value);
+        }
+    }
+}
+
+/// Error connecting a controller
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControllerError {
+    /// Built without the feature for this kind of device, or running on
+    /// the web
+    Unsupported(&'static str),
+    /// The gamepad library could not start
+    Gamepad(String),
+    /// No MIDI input has this name
+    MidiPortNotFound(String),
+    /// The MIDI input could not be opened
+    Midi(String),
+}
+
+impl std::fmt::Display for ControllerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ControllerError::Unsupported(feature) => write!(
+                f,
+                "This input needs a native build with the `{}` feature",
+                feature
+            ),
+            ControllerError::Gamepad(msg) => write!(f, "Gamepad error: {}", msg),
+            ControllerError::MidiPortNotFound(name) => write!(f, "No MIDI input named '{}'", name),
+            ControllerError::Midi(msg) => write!(f, "MIDI error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ControllerError {}
+
+/// Names of the MIDI inputs
+pub fn midi_ports() -> Vec<String> {
+    #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+    {
+        midi::ports()
+    }
+    #[cfg(not(all(feature = "midi", not(target_arch = "wasm32"))))]
+    {
+        Vec::new()
+    }
+}
+
+/// Connected devices feeding an [`InputMapper`]
+pub struct ControllerInput {
+    pub mapper: InputMapper,
+    #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+    gamepads: Option<gilrs::Gilrs>,
+    #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+    midi: Option<midi::MidiInput>,
+    /// Port the MIDI input reads from
+    midi_port: Option<String>,
+    /// Ports listed in the MIDI picker
+    midi_ports: Vec<String>,
+    error: Option<String>,
+}
+
+impl Default for ControllerInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControllerInput {
+    pub fn new() -> Self {
+        Self {
+            mapper: InputMapper::new(),
+            #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+            gamepads: None,
+            #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+            midi: None,
+            midi_port: None,
+            midi_ports: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Whether gamepads are read
+    pub fn gamepads_enabled(&self) -> bool {
+        #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+        {
+            self.gamepads.is_some()
+        }
+        #[cfg(not(all(feature = "gamepad", not(target_arch = "wasm32"))))]
+        {
+            false
+        }
+    }
+
+    /// Start or stop reading every connected gamepad
+    pub fn set_gamepads_enabled(&mut self, enabled: bool) -> Result<(), ControllerError> {
+        #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+        {
+            self.gamepads = None;
+            if enabled {
+                let gilrs =
+                    gilrs::Gilrs::new().map_err(|e| ControllerError::Gamepad(e.to_string()))?;
+                self.gamepads = Some(gilrs);
+            }
+            Ok(())
+        }
+        #[cfg(not(all(feature = "gamepad", not(target_arch = "wasm32"))))]
+        {
+            if enabled {
+                Err(ControllerError::Unsupported("gamepad"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Names of the connected gamepads
+    pub fn gamepad_names(&self) -> Vec<String> {
+        #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+        if let Some(gilrs) = &self.gamepads {
+            return gilrs
+                .gamepads()
+                .map(|(_, gamepad)| gamepad.name().to_string())
+                .collect();
+        }
+        Vec::new()
+    }
+
+    /// MIDI port read from
+    pub fn midi_port(&self) -> Option<&str> {
+        self.midi_port.as_deref()
+    }
+
+    /// Read MIDI from the named port, or stop reading MIDI with `None`
+    ///
+    /// On failure MIDI is turned off.
+    pub fn set_midi_port(&mut self, port: Option<String>) -> Result<(), ControllerError> {
+        #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+        {
+            self.midi = None;
+        }
+        self.midi_port = None;
+        let Some(name) = port else {
+            return Ok(());
+        };
+        #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+        {
+            self.midi = Some(midi::MidiInput::connect(&name)?);
+            self.midi_port = Some(name);
+            Ok(())
+        }
+        #[cfg(not(all(feature = "midi", not(target_arch = "wasm32"))))]
+        {
+            let _ = name;
+            Err(ControllerError::Unsupported("midi"))
+        }
+    }
+
+    /// Feed the events since the last call to the mapper and move the
+    /// relative mappings
+    pub fn update(&mut self, dt: f32) {
+        #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+        if let Some(gilrs) = &mut self.gamepads {
+            while let Some(event) = gilrs.next_event() {
+                if let Some(event) = gamepad::convert(event.event) {
+                    self.mapper.handle(event);
+                }
+            }
+        }
+        #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+        if let Some(midi) = &self.midi {
+            for event in midi.drain() {
+                self.mapper.handle(event);
+            }
+        }
+        self.mapper.update(dt);
+    }
+
+    /// Device pickers and the mapping editor, offering `targets` to map to
+    pub fn ui(&mut self, ui: &mut egui::Ui, targets: &[MappingTarget]) {
+        ui.horizontal(|ui| {
+            let mut enabled = self.gamepads_enabled();
+            if ui.checkbox(&mut enabled, "🎮 Gamepads").changed() {
+                self.error = self
+                    .set_gamepads_enabled(enabled)
+                    .err()
+                    .map(|e| e.to_string());
+            }
+            if enabled {
+                let names = self.gamepad_names();
+                let text = if names.is_empty() {
+                    "none connected".to_string()
+                } else {
+                    names.join(", ")
+                };
+                ui.label(egui::RichText::new(text).small().weak());
+            }
+        });
+
+        let mut selected_port = None;
+        ui.horizontal(|ui| {
+            ui.label("🎹 MIDI:");
+            egui::ComboBox::from_id_salt("controller_midi_port")
+                .selected_text(self.midi_port.as_deref().unwrap_or("Off"))
+                .width(180.0)
+                .show_ui(ui, |ui| {
+                    if ui
+                        .selectable_label(self.midi_port.is_none(), "Off")
+                        .clicked()
+                    {
+                        selected_port = Some(None);
+                    }
+                    for port in &self.midi_ports {
+                        let current = self.midi_port.as_ref() == Some(port);
+                        if ui.selectable_label(current, port).clicked() {
+                            selected_port = Some(Some(port.clone()));
+                        }
+                    }
+                });
+            if ui
+                .small_button("⟳")
+                .on_hover_text("List the MIDI inputs again")
+                .clicked()
+            {
+                self.midi_ports = midi_ports();
+            }
+        });
+        if let Some(port) = selected_port {
+            self.error = self.set_midi_port(port).err().map(|e| e.to_string());
+        }
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), error);
+        }
+
+        ui.add_space(5.0);
+        self.mappings_ui(ui, targets);
+    }
+
+    fn mappings_ui(&mut self, ui: &mut egui::Ui, targets: &[MappingTarget]) {
+        let mut remove = None;
+        let mut learn = None;
+        let learning = self.mapper.learning();
+        let mut controls: Vec<InputControl> = GamepadAxis::ALL
+            .into_iter()
+            .map(InputControl::Axis)
+            .chain(GamepadButton::ALL.into_iter().map(InputControl::Button))
+            .collect();
+        let mut midi: Vec<InputControl> = self
+            .mapper
+            .seen_controls()
+            .filter(|control| matches!(control, InputControl::MidiCc { .. }))
+            .collect();
+        midi.sort_by_key(|control| control.label());
+        controls.extend(midi);
+
+        egui::Grid::new("controller_mappings")
+            .striped(true)
+            .num_columns(6)
+            .show(ui, |ui| {
+                for header in ["Control", "Target", "Range", "Mode", "Value", ""] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for (index, mapping) in self.mapper.mappings.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        let text = mapping
+                            .control
+                            .map_or("Not set".to_string(), |control| control.label());
+                        let before = mapping.control;
+                        egui::ComboBox::from_id_salt(("mapping_control", index))
+                            .selected_text(text)
+                            .width(150.0)
+                            .show_ui(ui, |ui| {
+                                for control in &controls {
+                                    ui.selectable_value(
+                                        &mut mapping.control,
+                                        Some(*control),
+                                        control.label(),
+                                    );
+                                }
+                            });
+                        if mapping.control != before {
+                            mapping.reset();
+                        }
+                        let label = if learning == Some(index) {
+                            "Move a control…"
+                        } else {
+                            "🎯 Learn"
+                        };
+                        if ui
+                            .small_button(label)
+                            .on_hover_text("Bind the next control that moves")
+                            .clicked()
+                        {
+                            learn = Some(index);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_salt(("mapping_target", index))
+                            .selected_text(mapping.target.as_str())
+                            .width(120.0)
+                            .show_ui(ui, |ui| {
+                                for target in targets {
+                                    ui.selectable_value(
+                                        &mut mapping.target,
+                                        target.name.clone(),
+                                        target.name.as_str(),
+                                    );
+                                }
+                            });
+                        let components = targets
+                            .iter()
+                            .find(|target| target.name == mapping.target)
+                            .map_or(1, |target| target.components);
+                        mapping.component = mapping.component.min(components - 1);
+                        if components > 1 {
+                            egui::ComboBox::from_id_salt(("mapping_component", index))
+                                .selected_text(COMPONENT_NAMES[mapping.component])
+                                .width(36.0)
+                                .show_ui(ui, |ui| {
+                                    for (component, name) in
+                                        COMPONENT_NAMES.iter().enumerate().take(components)
+                                    {
+                                        ui.selectable_value(
+                                            &mut mapping.component,
+                                            component,
+                                            *name,
+                                        );
+                                    }
+                                });
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut mapping.min).speed(0.01));
+                        ui.label("to");
+                        ui.add(egui::DragValue::new(&mut mapping.max).speed(0.01));
+                    });
+
+                    egui::ComboBox::from_id_salt(("mapping_mode", index))
+                        .selected_text(mapping.mode.name())
+                        .width(80.0)
+                        .show_ui(ui, |ui| {
+                            for mode in MappingMode::ALL {
+                                ui.selectable_value(&mut mapping.mode, mode, mode.name());
+                            }
+                        });
+
+                    match mapping.value {
+                        Some(value) => {
+                            let span = mapping.max - mapping.min;
+                            let fill = if span == 0.0 {
+                                0.0
+                            } else {
+                                (value - mapping.min) / span
+                            };
+                            ui.add(
+                                egui::ProgressBar::new(fill)
+                                    .desired_width(80.0)
+                                    .text(format!("{:.2}", value)),
+                            );
+                        }
+                        None => {
+                            ui.label(egui::RichText::new("waiting").small().weak());
+                        }
+                    }
+
+                    if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(index) = learn {
+            self.mapper.learn(index);
+        }
+        if let Some(index) = remove {
+            self.mapper.remove(index);
+        }
+        if ui.button("➕ Add Mapping").clicked() {
+            let target = targets.first().map_or("", |target| target.name.as_str());
+            self.mapper.add(InputMapping::new(None, target));
+        }
+        if targets.is_empty() {
+            ui.label(
+                egui::RichText::new("The shader has no uniforms to map yet.")
+                    .small()
+                    .weak(),
+            );
+        }
+    }
+}
+
+/// Names of the components of a vector target
+const COMPONENT_NAMES: [&str; 4] = ["x", "y", "z", "w"];
+
+/// Gamepad events from gilrs
+#[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+mod gamepad {
+    use gilrs::{Axis, Button, EventType};
+
+    use super::{GamepadAxis, GamepadButton, InputControl, InputEvent};
+
+    /// The event of a mappable control, `None` for connections and unknown
+    /// controls
+    pub fn convert(event: EventType) -> Option<InputEvent> {
+        let (control, value) = match event {
+            EventType::AxisChanged(axis, value, _) => (InputControl::Axis(axis_of(axis)?), value),
+            EventType::ButtonChanged(button, value, _) => {
+                (InputControl::Button(button_of(button)?), value)
+            }
+            _ => return None,
+        };
+        Some(InputEvent { control, value })
+    }
+
+    fn axis_of(axis: Axis) -> Option<GamepadAxis> {
+        Some(match axis {
+            Axis::LeftStickX => GamepadAxis::LeftStickX,
+            Axis::LeftStickY => GamepadAxis::LeftStickY,
+            Axis::RightStickX => GamepadAxis::RightStickX,
+            Axis::RightStickY => GamepadAxis::RightStickY,
+            Axis::LeftZ => GamepadAxis::LeftZ,
+            Axis::RightZ => GamepadAxis::RightZ,
+            Axis::DPadX => GamepadAxis::DPadX,
+            Axis::DPadY => GamepadAxis::DPadY,
+            _ => return None,
+        })
+    }
+
+    fn button_of(button: Button) -> Option<GamepadButton> {
+        Some(match button {
+            Button::South => GamepadButton::South,
+            Button::East => GamepadButton::East,
+            Button::North => GamepadButton::North,
+            Button::West => GamepadButton::West,
+            Button::LeftTrigger => GamepadButton::LeftBumper,
+            Button::RightTrigger => GamepadButton::RightBumper,
+            Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+            Button::RightTrigger2 => GamepadButton::RightTrigger,
+            Button::Select => GamepadButton::Select,
+            Button::Start => GamepadButton::Start,
+            Button::Mode => GamepadButton::Mode,
+            Button::LeftThumb => GamepadButton::LeftThumb,
+            Button::RightThumb => GamepadButton::RightThumb,
+            Button::DPadUp => GamepadButton::DPadUp,
+            Button::DPadDown => GamepadButton::DPadDown,
+            Button::DPadLeft => GamepadButton::DPadLeft,
+            Button::DPadRight => GamepadButton::DPadRight,
+            _ => return None,
+        })
+    }
+}
+
+/// MIDI input with midir
+#[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+mod midi {
+    use std::sync::mpsc::{channel, Receiver};
+
+    use super::{parse_midi, ControllerError, InputEvent};
+
+    /// Name the playground shows up as to other MIDI software
+    const CLIENT_NAME: &str = "wgpu_playground";
+
+    pub fn ports() -> Vec<String> {
+        let Ok(input) = midir::MidiInput::new(CLIENT_NAME) else {
+            return Vec::new();
+        };
+        input
+            .ports()
+            .iter()
+            .filter_map(|port| input.port_name(port).ok())
+            .collect()
+    }
+
+    /// An open MIDI port and the control changes it received since the last
+    /// drain
+    pub struct MidiInput {
+        _connection: midir::MidiInputConnection<()>,
+        events: Receiver<InputEvent>,
+    }
+
+    impl MidiInput {
+        pub fn connect(name: &str) -> Result<Self, ControllerError> {
+            let input = midir::MidiInput::new(CLIENT_NAME)
+                .map_err(|e| ControllerError::Midi(e.to_string()))?;
+            let port = input
+                .ports()
+                .into_iter()
+                .find(|port| input.port_name(port).is_ok_and(|n| n == name))
+                .ok_or_else(|| ControllerError::MidiPortNotFound(name.to_string()))?;
+            let (sender, events) = channel();
+            let connection = input
+                .connect(
+                    &port,
+                    CLIENT_NAME,
+                    move |_, message, _| {
+                        if let Some(event) = parse_midi(message) {
+                            let _ = sender.send(event);
+                        }
+                    },
+                    (),
+                )
+                .map_err(|e| ControllerError::Midi(e.to_string()))?;
+            Ok(Self {
+                _connection: connection,
+                events,
+            })
+        }
+
+        pub fn drain(&self) -> Vec<InputEvent> {
+            self.events.try_iter().collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STICK: InputControl = InputControl::Axis(GamepadAxis::LeftStickX);
+    const BUTTON: InputControl = InputControl::Button(GamepadButton::South);
+    const KNOB: InputControl = InputControl::MidiCc {
+        channel: 2,
+        controller: 74,
+    };
+
+    fn event(control: InputControl, value: f32) -> InputEvent {
+        InputEvent { control, value }
+    }
+
+    #[test]
+    fn test_parse_midi() {
+        assert_eq!(parse_midi(&[0xB2, 74, 127]), Some(event(KNOB, 1.0)));
+        assert_eq!(parse_midi(&[0xB2, 74, 0]), Some(event(KNOB, 0.0)));
+        // Note on and truncated messages
+        assert_eq!(parse_midi(&[0x92, 60, 100]), None);
+        assert_eq!(parse_midi(&[0xB2, 74]), None);
+        assert_eq!(KNOB.label(), "🎹 MIDI ch 3 CC 74");
+    }
+
+    #[test]
+    fn test_absolute_mapping() {
+        let mut mapper = InputMapper::new();
+        mapper.add(InputMapping::new(Some(STICK), "zoom").with_range(1.0, 3.0));
+        mapper.add(InputMapping::new(Some(KNOB), "tint_color").with_component(2));
+        assert_eq!(mapper.outputs().count(), 0);
+
+        // Inside the dead zone the stick reads centered
+        mapper.handle(event(STICK, 0.05));
+        mapper.handle(event(KNOB, 0.25));
+        let outputs: Vec<_> = mapper.outputs().collect();
+        assert_eq!(outputs, vec![("zoom", 0, 2.0), ("tint_color", 2, 0.25)]);
+
+        mapper.handle(event(STICK, -1.0));
+        assert_eq!(mapper.mappings()[0].value(), Some(1.0));
+    }
+
+    #[test]
+    fn test_toggle_flips_on_press() {
+        let mut mapper = InputMapper::new();
+        mapper.add(InputMapping::new(Some(BUTTON), "enabled").with_mode(MappingMode::Toggle));
+        let value = |mapper: &InputMapper| mapper.mappings()[0].value();
+
+        mapper.handle(event(BUTTON, 1.0));
+        assert_eq!(value(&mapper), Some(1.0));
+        // Holding and releasing keeps it
+        mapper.handle(event(BUTTON, 0.9));
+        mapper.handle(event(BUTTON, 0.0));
+        assert_eq!(value(&mapper), Some(1.0));
+        mapper.handle(event(BUTTON, 1.0));
+        assert_eq!(value(&mapper), Some(0.0));
+    }
+
+    #[test]
+    fn test_relative_mapping_moves_while_held() {
+        let mut mapper = InputMapper::new();
+        mapper.add(
+            InputMapping::new(Some(STICK), "offset")
+                .with_range(-1.0, 1.0)
+                .with_mode(MappingMode::Relative),
+        );
+        mapper.update(1.0);
+        assert_eq!(mapper.mappings()[0].value(), None);
+
+        mapper.handle(event(STICK, 1.0));
+        assert_eq!(mapper.mappings()[0].value(), Some(0.0));
+        mapper.update(0.5);
+        assert_eq!(mapper.mappings()[0].value(), Some(0.5));
+        // Clamped to the range
+        mapper.update(10.0);
+        assert_eq!(mapper.mappings()[0].value(), Some(1.0));
+        // Let go, it stays
+        mapper.handle(event(STICK, 0.0));
+        mapper.update(1.0);
+        assert_eq!(mapper.mappings()[0].value(), Some(1.0));
+    }
+
+    #[test]
+    fn test_learn_binds_the_next_control() {
+        let mut mapper = InputMapper::new();
+        mapper.add(InputMapping::new(None, "speed"));
+        mapper.add(InputMapping::new(None, "size"));
+        mapper.learn(1);
+
+        // A resting stick doesn't count
+        mapper.handle(event(STICK, 0.1));
+        assert_eq!(mapper.learning(), Some(1));
+        mapper.handle(event(KNOB, 0.5));
+        assert_eq!(mapper.learning(), None);
+        assert_eq!(mapper.mappings()[1].control, Some(KNOB));
+        assert_eq!(mapper.mappings()[1].value(), Some(0.5));
+        assert_eq!(mapper.mappings()[0].control, None);
+
+        mapper.learn(0);
+        mapper.remove(0);
+        assert_eq!(mapper.learning(), None);
+        assert_eq!(mapper.mappings().len(), 1);
+    }
+
+    #[test]
+    fn test_apply_to_uniforms() {
+        let mut uniforms = UniformTweaker::from_wgsl(
+            "struct Params { tint_color: vec3<f32>, speed: f32 }\n\
+             @group(1) @binding(0) var<uniform> params: Params;\n\
+             @fragment fn main() -> @location(0) vec4<f32> { return vec4<f32>(params.tint_color, params.speed); }",
+        )
+        .unwrap();
+        let targets = uniform_targets(&uniforms, |_| true);
+        assert_eq!(
+            targets,
+            vec![
+                MappingTarget {
+                    name: "tint_color".to_string(),
+                    components: 3
+                },
+                MappingTarget {
+                    name: "speed".to_string(),
+                    components: 1
+                },
+            ]
+        );
+
+        let mut mapper = InputMapper::new();
+        mapper.add(InputMapping::new(Some(KNOB), "tint_color").with_component(1));
+        mapper.add(InputMapping::new(Some(BUTTON), "speed").with_range(0.0, 4.0));
+        mapper.handle(event(KNOB, 0.0));
+        mapper.handle(event(BUTTON, 0.5));
+        mapper.apply(&mut uniforms);
+        assert_eq!(uniforms.get("tint_color"), Some(vec![1.0, 0.0, 1.0]));
+        assert_eq!(uniforms.get("speed"), Some(vec![2.0]));
+    }
+
+    #[cfg(not(feature = "gamepad"))]
+    #[test]
+    fn test_gamepads_need_the_feature() {
+        let mut input = ControllerInput::new();
+        assert_eq!(
+            input.set_gamepads_enabled(true),
+            Err(ControllerError::Unsupported("gamepad"))
+        );
+        assert!(!input.gamepads_enabled());
+    }
+
+    #[cfg(not(feature = "midi"))]
+    #[test]
+    fn test_midi_needs_the_feature() {
+        let mut input = ControllerInput::new();
+        assert_eq!(
+            input.set_midi_port(Some("keys".to_string())),
+            Err(ControllerError::Unsupported("midi"))
+        );
+        assert_eq!(input.midi_port(), None);
+        assert!(midi_ports().is_empty());
+    }
+}
@@ -0,0 +1,572 @@
+//! CPU-side cost of recording, submitting and presenting each frame
+//!
+//! The render loop brackets every frame with [`CpuProfiler::begin_frame`]
+//! and [`CpuProfiler::end_frame`] and marks its stages with [`cpu_scope!`]:
+//! acquiring the surface texture, recording the command encoder, the
+//! `queue.submit` call and `present`. Scopes nest, so the recording stage
+//! is broken down further into the egui frame, uploads and render passes.
+//! The history feeds the flame graph of
+//! [`crate::cpu_profiler_panel::CpuProfilerPanel`].
+//!
+//! Time spent blocked in acquire or present means the CPU is waiting for
+//! the GPU or the display; time spent recording or submitting is CPU cost
+//! that a faster GPU would not remove. [`Bottleneck::classify`] makes that
+//! call from the history.
+//!
+//! Every [`cpu_scope!`] is also a span of the `profiling` crate. The core's
+//! hot paths (pipeline creation, texture uploads, preview renders and
+//! readbacks) are marked the same way, so with the `puffin` or `tracy`
+//! feature they show up in those profilers as well: in the Puffin Viewer
+//! tab, in `puffin_viewer` after [`serve_puffin`] has started the server,
+//! or in Tracy after [`start_tracy`].
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+
+pub use profiling;
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+pub use puffin;
+
+/// Number of frames kept in the history
+pub const HISTORY_FRAMES: usize = 300;
+
+/// Blocked in `get_current_texture` until a surface texture is free
+pub const SCOPE_ACQUIRE: &str = "Acquire";
+/// Recording the frame's command encoder, including the UI
+pub const SCOPE_RECORD: &str = "Record";
+/// The `queue.submit` call
+pub const SCOPE_SUBMIT: &str = "Submit";
+/// The `present` call
+pub const SCOPE_PRESENT: &str = "Present";
+
+/// Default address of the puffin server
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+pub const PUFFIN_ADDRESS: &str = "127.0.0.1:8585";
+
+/// Time a named scope through the global profiler and the `profiling`
+/// backend, if one is enabled
+///
+/// The scope lasts until the end of the enclosing block.
+#[macro_export]
+macro_rules! cpu_scope {
+    ($name:expr) => {
+        let _cpu_scope = $crate::cpu_profiler::CpuProfiler::global().scope($name);
+        $crate::cpu_profiler::profiling::scope!($name);
+    };
+}
+
+/// Start a puffin server that `puffin_viewer` can connect to and turn on
+/// puffin scopes; the server stops when the returned value is dropped
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+pub fn serve_puffin(address: &str) -> Result<puffin_http::Server, String> {
+    let server = puffin_http::Server::new(address).map_err(|e| e.to_string())?;
+    puffin::set_scopes_on(true);
+    Ok(server)
+}
+
+/// Connect to a Tracy profiler; spans before this are dropped
+#[cfg(all(feature = "tracy", not(target_arch = "wasm32")))]
+pub fn start_tracy() {
+    profiling::tracy_client::Client::start();
+}
+
+/// One timed scope within a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpuScope {
+    pub name: Cow<'static, str>,
+    /// Number of enclosing scopes
+    pub depth: usize,
+    /// Offset from the start of the frame
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl CpuScope {
+    pub fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+/// The scopes of one completed frame, in the order they began
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CpuFrame {
+    pub frame: u64,
+    pub duration: Duration,
+    pub scopes: Vec<CpuScope>,
+}
+
+impl CpuFrame {
+    /// Total time of the top-level scopes called `name`
+    pub fn top_level(&self, name: &str) -> Duration {
+        self.scopes
+            .iter()
+            .filter(|s| s.depth == 0 && s.name == name)
+            .map(|s| s.duration)
+            .sum()
+    }
+
+    /// The scope at `depth` covering `offset`
+    pub fn scope_at(&self, depth: usize, offset: Duration) -> Option<&CpuScope> {
+        self.scopes
+            .iter()
+            .find(|s| s.depth == depth && s.start <= offset && offset < s.end())
+    }
+
+    /// Number of nesting levels
+    pub fn depth(&self) -> usize {
+        self.scopes.iter().map(|s| s.depth + 1).max().unwrap_or(0)
+    }
+}
+
+/// Time per frame of a scope, averaged over the history
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeStats {
+    pub name: Cow<'static, str>,
+    pub depth: usize,
+    /// Mean time per frame; a scope that runs in only some frames counts
+    /// as zero in the others
+    pub mean: Duration,
+    pub max: Duration,
+    /// Share of the mean frame time
+    pub share: f32,
+}
+
+impl ScopeStats {
+    /// Stats for every scope, keyed by name and depth, in the order they
+    /// first appear
+    pub fn from_frames(frames: &[CpuFrame]) -> Vec<Self> {
+        if frames.is_empty() {
+            return Vec::new();
+        }
+        let count = frames.len() as u32;
+        let mean_frame = frames.iter().map(|f| f.duration).sum::<Duration>() / count;
+
+        let mut keys: Vec<(usize, &Cow<'static, str>)> = Vec::new();
+        for scope in frames.iter().flat_map(|f| &f.scopes) {
+            if !keys.contains(&(scope.depth, &scope.name)) {
+                keys.push((scope.depth, &scope.name));
+            }
+        }
+
+        keys.into_iter()
+            .map(|(depth, name)| {
+                let per_frame: Vec<Duration> = frames
+                    .iter()
+                    .map(|f| {
+                        f.scopes
+                            .iter()
+                            .filter(|s| s.depth == depth && s.name == *name)
+                            .map(|s| s.duration)
+                            .sum()
+                    })
+                    .collect();
+                let mean = per_frame.iter().sum::<Duration>() / count;
+                Self {
+                    name: name.clone(),
+                    depth,
+                    mean,
+                    max: per_frame.iter().copied().max().unwrap_or_default(),
+                    share: if mean_frame.is_zero() {
+                        0.0
+                    } else {
+                        (mean.as_secs_f64() / mean_frame.as_secs_f64()) as f32
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Where the CPU side of a frame spends most of its time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bottleneck {
+    /// Building the UI and recording commands
+    Recording,
+    /// Inside `queue.submit`, where the driver validates and translates the
+    /// commands; on GL most of the GPU work is issued here too
+    Submit,
+    /// Blocked in acquire or present: the GPU or the display sets the pace
+    Waiting,
+}
+
+impl Bottleneck {
+    /// Compare the mean time blocked in acquire and present, spent in
+    /// submit, and spent on everything else
+    pub fn classify(frames: &[CpuFrame]) -> Option<Self> {
+        if frames.is_empty() {
+            return None;
+        }
+        let count = frames.len() as u32;
+        let mean = |f: fn(&CpuFrame) -> Duration| frames.iter().map(f).sum::<Duration>() / count;
+        let frame = mean(|f| f.duration);
+        let waiting = mean(|f| f.top_level(SCOPE_ACQUIRE) + f.top_level(SCOPE_PRESENT));
+        let submit = mean(|f| f.top_level(SCOPE_SUBMIT));
+        let recording = frame.saturating_sub(waiting + submit);
+        if frame.is_zero() {
+            None
+        } else if waiting >= recording && waiting >= submit {
+            Some(Self::Waiting)
+        } else if submit > recording {
+            Some(Self::Submit)
+        } else {
+            Some(Self::Recording)
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Recording => {
+                "CPU bound: most of the frame goes into building the UI and recording commands. A faster GPU would not help; record less work or cache it."
+            }
+            Self::Submit => {
+                "Submit bound: queue.submit dominates. The driver validates and translates the commands there, and on GL it issues most of the GPU work as well."
+            }
+            Self::Waiting => {
+                "GPU or display bound: the CPU mostly waits in acquire or present. With Fifo that is vsync; otherwise the GPU has not finished the previous frames."
+            }
+        }
+    }
+}
+
+/// The frame being recorded
+#[derive(Default)]
+struct Recorder {
+    frame: u64,
+    /// Changes with every begun frame, so a guard outliving its frame
+    /// cannot end a scope of the next one
+    generation: u64,
+    frame_start: Option<Instant>,
+    /// Thread that began the frame; scopes on other threads are ignored
+    /// since they would not nest with the frame's
+    thread: Option<ThreadId>,
+    scopes: Vec<CpuScope>,
+    /// Indices into `scopes` of the scopes not yet ended, with their start
+    open: Vec<(usize, Instant)>,
+    history: VecDeque<CpuFrame>,
+}
+
+/// Ends its scope when dropped
+#[must_use = "the scope ends when the guard is dropped"]
+pub struct ScopeGuard<'a> {
+    profiler: &'a CpuProfiler,
+    /// Frame generation and scope index, or `None` outside a frame
+    scope: Option<(u64, usize)>,
+}
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        if let Some((generation, index)) = self.scope {
+            self.profiler
+                .end_scope_at(generation, index, Instant::now());
+        }
+    }
+}
+
+/// Records nested, named CPU timings per frame
+///
+/// Scopes begun outside a frame or on another thread than the frame's are
+/// ignored.
+#[derive(Default)]
+pub struct CpuProfiler {
+    recorder: Mutex<Recorder>,
+}
+
+impl CpuProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The profiler the render loop reports to
+    pub fn global() -> &'static CpuProfiler {
+        static GLOBAL_PROFILER: OnceLock<CpuProfiler> = OnceLock::new();
+        GLOBAL_PROFILER.get_or_init(CpuProfiler::new)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Recorder> {
+        self.recorder.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start a frame, dropping one that was begun but never ended
+    pub fn begin_frame(&self) {
+        self.begin_frame_at(Instant::now());
+    }
+
+    fn begin_frame_at(&self, now: Instant) {
+        let mut recorder = self.lock();
+        recorder.generation += 1;
+        recorder.frame_start = Some(now);
+        recorder.thread = Some(std::thread::current().id());
+        recorder.scopes.clear();
+        recorder.open.clear();
+    }
+
+    /// Time `name` until the returned guard is dropped
+    pub fn scope(&self, name: &'static str) -> ScopeGuard<'_> {
+        // Outside a frame the clock is not read, which keeps the hot path
+        // scopes free on the web, where no frame is ever begun and
+        // `Instant::now` is unavailable
+        let scope = if self.lock().frame_start.is_some() {
+            self.begin_scope_at(name, Instant::now())
+        } else {
+            None
+        };
+        ScopeGuard {
+            profiler: self,
+            scope,
+        }
+    }
+
+    fn begin_scope_at(&self, name: &'static str, now: Instant) -> Option<(u64, usize)> {
+        let mut recorder = self.lock();
+        let frame_start = recorder.frame_start?;
+        if recorder.thread != Some(std::thread::current().id()) {
+            return None;
+        }
+        let index = recorder.scopes.len();
+        let depth = recorder.open.len();
+        recorder.scopes.push(CpuScope {
+            name: Cow::Borrowed(name),
+            depth,
+            start: now.saturating_duration_since(frame_start),
+            duration: Duration::ZERO,
+        });
+        recorder.open.push((index, now));
+        Some((recorder.generation, index))
+    }
+
+    fn end_scope_at(&self, generation: u64, index: usize, now: Instant) {
+        let mut recorder = self.lock();
+        if recorder.generation != generation || recorder.frame_start.is_none() {
+            return;
+        }
+        let Some(position) = recorder.open.iter().position(|(i, _)| *i == index) else {
+            return;
+        };
+        let (_, start) = recorder.open.remove(position);
+        recorder.scopes[index].duration = now.saturating_duration_since(start);
+    }
+
+    /// Complete the frame and add it to the history; also ends the frame
+    /// of the `profiling` backend
+    pub fn end_frame(&self) {
+        if self.end_frame_at(Instant::now()) {
+            profiling::finish_frame!();
+        }
+    }
+
+    /// Returns false if no frame was in progress
+    fn end_frame_at(&self, now: Instant) -> bool {
+        let mut recorder = self.lock();
+        let Some(frame_start) = recorder.frame_start.take() else {
+            return false;
+        };
+        // Scopes still open end with the frame
+        for (index, start) in std::mem::take(&mut recorder.open) {
+            recorder.scopes[index].duration = now.saturating_duration_since(start);
+        }
+        let frame = CpuFrame {
+            frame: recorder.frame,
+            duration: now.saturating_duration_since(frame_start),
+            scopes: std::mem::take(&mut recorder.scopes),
+        };
+        if recorder.history.len() == HISTORY_FRAMES {
+            recorder.history.pop_front();
+        }
+        recorder.history.push_back(frame);
+        recorder.frame += 1;
+        true
+    }
+
+    /// Drop the current frame, e.g. when no surface texture was available
+    pub fn cancel_frame(&self) {
+        let mut recorder = self.lock();
+        recorder.frame_start = None;
+        recorder.scopes.clear();
+        recorder.open.clear();
+    }
+
+    /// The completed frames, oldest first
+    pub fn frames(&self) -> Vec<CpuFrame> {
+        self.lock().history.iter().cloned().collect()
+    }
+
+    /// Forget the history
+    pub fn clear(&self) {
+        self.lock().history.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    /// Record one frame of `frame_ms` with top-level scopes given as
+    /// `(name, start_ms, end_ms)`
+    fn record_frame(profiler: &CpuProfiler, frame_ms: u64, scopes: &[(&'static str, u64, u64)]) {
+        let base = Instant::now();
+        profiler.begin_frame_at(base);
+        for (name, start, end) in scopes {
+            let (generation, index) = profiler.begin_scope_at(name, base + ms(*start)).unwrap();
+            profiler.end_scope_at(generation, index, base + ms(*end));
+        }
+        profiler.end_frame_at(base + ms(frame_ms));
+    }
+
+    #[test]
+    fn test_scopes_nest() {
+        let profiler = CpuProfiler::new();
+        let base = Instant::now();
+        profiler.begin_frame_at(base);
+        let (generation, record) = profiler.begin_scope_at(SCOPE_RECORD, base).unwrap();
+        let (_, pass) = profiler.begin_scope_at("Pass", base + ms(2)).unwrap();
+        profiler.end_scope_at(generation, pass, base + ms(5));
+        profiler.end_scope_at(generation, record, base + ms(6));
+        let (_, submit) = profiler.begin_scope_at(SCOPE_SUBMIT, base + ms(6)).unwrap();
+        profiler.end_scope_at(generation, submit, base + ms(7));
+        profiler.end_frame_at(base + ms(8));
+
+        let frames = profiler.frames();
+        assert_eq!(frames.len(), 1);
+        let frame = &frames[0];
+        assert_eq!(frame.duration, ms(8));
+        assert_eq!(frame.depth(), 2);
+        let layout: Vec<(&str, usize, Duration, Duration)> = frame
+            .scopes
+            .iter()
+            .map(|s| (s.name.as_ref(), s.depth, s.start, s.duration))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                (SCOPE_RECORD, 0, ms(0), ms(6)),
+                ("Pass", 1, ms(2), ms(3)),
+                (SCOPE_SUBMIT, 0, ms(6), ms(1)),
+            ]
+        );
+        assert_eq!(
+            frame.scope_at(1, ms(3)).map(|s| s.name.as_ref()),
+            Some("Pass")
+        );
+        assert_eq!(frame.scope_at(1, ms(5)), None);
+        assert_eq!(frame.top_level(SCOPE_SUBMIT), ms(1));
+    }
+
+    #[test]
+    fn test_scopes_outside_a_frame_are_ignored() {
+        let profiler = CpuProfiler::new();
+        assert!(profiler.begin_scope_at("Early", Instant::now()).is_none());
+        {
+            let _scope = profiler.scope("Early");
+        }
+        profiler.end_frame();
+        assert!(profiler.frames().is_empty());
+
+        // A cancelled frame is not recorded, and its scopes end harmlessly
+        profiler.begin_frame();
+        let scope = profiler.scope(SCOPE_ACQUIRE);
+        profiler.cancel_frame();
+        drop(scope);
+        profiler.end_frame();
+        assert!(profiler.frames().is_empty());
+
+        // A guard kept past its frame does not end a scope of the next one
+        profiler.begin_frame();
+        let stale = profiler.scope("Stale");
+        profiler.begin_frame();
+        let _record = profiler.scope(SCOPE_RECORD);
+        drop(stale);
+        let recorder = profiler.lock();
+        assert_eq!(recorder.scopes.len(), 1);
+        assert_eq!(recorder.open.len(), 1);
+        drop(recorder);
+
+        // Nor do scopes on other threads
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let _worker = profiler.scope("Worker");
+            });
+        });
+        assert_eq!(profiler.lock().scopes.len(), 1);
+    }
+
+    #[test]
+    fn test_open_scopes_end_with_the_frame() {
+        let profiler = CpuProfiler::new();
+        let base = Instant::now();
+        profiler.begin_frame_at(base);
+        profiler.begin_scope_at(SCOPE_RECORD, base + ms(1)).unwrap();
+        profiler.end_frame_at(base + ms(4));
+        assert_eq!(profiler.frames()[0].scopes[0].duration, ms(3));
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let profiler = CpuProfiler::new();
+        for _ in 0..HISTORY_FRAMES + 5 {
+            record_frame(&profiler, 1, &[]);
+        }
+        let frames = profiler.frames();
+        assert_eq!(frames.len(), HISTORY_FRAMES);
+        assert_eq!(frames[0].frame, 5);
+        profiler.clear();
+        assert!(profiler.frames().is_empty());
+    }
+
+    #[test]
+    fn test_scope_stats() {
+        let profiler = CpuProfiler::new();
+        record_frame(&profiler, 10, &[(SCOPE_RECORD, 0, 4), ("Capture", 4, 8)]);
+        record_frame(&profiler, 10, &[(SCOPE_RECORD, 0, 6)]);
+        let stats = ScopeStats::from_frames(&profiler.frames());
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, SCOPE_RECORD);
+        assert_eq!(stats[0].mean, ms(5));
+        assert_eq!(stats[0].max, ms(6));
+        assert!((stats[0].share - 0.5).abs() < 1e-6);
+        // Only in the first frame
+        assert_eq!(stats[1].name, "Capture");
+        assert_eq!(stats[1].mean, ms(2));
+        assert!(ScopeStats::from_frames(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_bottleneck() {
+        let classify = |scopes: &[(&'static str, u64, u64)]| {
+            let profiler = CpuProfiler::new();
+            record_frame(&profiler, 20, scopes);
+            Bottleneck::classify(&profiler.frames())
+        };
+        assert_eq!(
+            classify(&[
+                (SCOPE_ACQUIRE, 0, 14),
+                (SCOPE_RECORD, 14, 18),
+                (SCOPE_SUBMIT, 18, 19)
+            ]),
+            Some(Bottleneck::Waiting)
+        );
+        assert_eq!(
+            classify(&[
+                (SCOPE_ACQUIRE, 0, 1),
+                (SCOPE_RECORD, 1, 17),
+                (SCOPE_SUBMIT, 17, 19)
+            ]),
+            Some(Bottleneck::Recording)
+        );
+        assert_eq!(
+            classify(&[
+                (SCOPE_ACQUIRE, 0, 1),
+                (SCOPE_RECORD, 1, 5),
+                (SCOPE_SUBMIT, 5, 18)
+            ]),
+            Some(Bottleneck::Submit)
+        );
+        assert_eq!(Bottleneck::classify(&[]), None);
+    }
+}
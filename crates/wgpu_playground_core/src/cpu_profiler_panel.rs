@@ -0,0 +1,425 @@
+use std::time::Duration;
+
+use egui::{Color32, Pos2, Rect, Vec2};
+
+use crate::cpu_profiler::{
+    Bottleneck, CpuFrame, CpuProfiler, CpuScope, ScopeStats, HISTORY_FRAMES, SCOPE_ACQUIRE,
+    SCOPE_PRESENT, SCOPE_RECORD, SCOPE_SUBMIT,
+};
+
+/// Height of one nesting level in the flame graph
+const ROW_HEIGHT: f32 = 22.0;
+
+/// Height of the frame history strip
+const STRIP_HEIGHT: f32 = 70.0;
+
+/// Bars narrower than this get no label
+const MIN_LABEL_WIDTH: f32 = 40.0;
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Color of a scope; the waiting stages share a muted color so CPU work
+/// stands out
+fn scope_color(name: &str) -> Color32 {
+    match name {
+        SCOPE_ACQUIRE | SCOPE_PRESENT => Color32::from_rgb(90, 110, 140),
+        SCOPE_RECORD => Color32::from_rgb(230, 150, 60),
+        SCOPE_SUBMIT => Color32::from_rgb(210, 80, 70),
+        _ => {
+            // Stable per name, so a scope keeps its color between frames
+            let hash = name
+                .bytes()
+                .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+            egui::ecolor::Hsva::new((hash % 360) as f32 / 360.0, 0.45, 0.85, 1.0).into()
+        }
+    }
+}
+
+/// The frame numbered `selected`, or the newest one
+fn selected_frame(frames: &[CpuFrame], selected: Option<u64>) -> Option<&CpuFrame> {
+    match selected {
+        Some(number) => frames
+            .iter()
+            .find(|f| f.frame == number)
+            .or_else(|| frames.last()),
+        None => frames.last(),
+    }
+}
+
+fn slowest_frame(frames: &[CpuFrame]) -> Option<u64> {
+    frames.iter().max_by_key(|f| f.duration).map(|f| f.frame)
+}
+
+/// Bar of `scope` in a flame graph of a frame lasting `frame`, drawn in
+/// `track` one row below its parent
+fn scope_rect(scope: &CpuScope, frame: Duration, track: Rect) -> Rect {
+    let x = |offset: Duration| {
+        let t = if frame.is_zero() {
+            0.0
+        } else {
+            (offset.as_secs_f64() / frame.as_secs_f64()).min(1.0) as f32
+        };
+        track.left() + t * track.width()
+    };
+    let left = x(scope.start);
+    let right = x(scope.end()).max(left + 1.0);
+    let top = track.top() + (scope.depth + 1) as f32 * ROW_HEIGHT;
+    Rect::from_min_max(
+        Pos2::new(left, top),
+        Pos2::new(right, top + ROW_HEIGHT - 2.0),
+    )
+}
+
+/// UI panel showing where the CPU spends each frame: a history of frame
+/// times split into waiting, recording and submitting, a flame graph of
+/// one frame and per-scope averages
+pub struct CpuProfilerPanel {
+    /// Snapshot of the history while paused
+    paused: Option<Vec<CpuFrame>>,
+    /// Frame shown in the flame graph; the newest when `None`
+    selected: Option<u64>,
+}
+
+impl Default for CpuProfilerPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CpuProfilerPanel {
+    pub fn new() -> Self {
+        Self {
+            paused: None,
+            selected: None,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Freeze the shown history on the profiler's current frames
+    pub fn pause(&mut self, profiler: &CpuProfiler) {
+        self.paused = Some(profiler.frames());
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = None;
+        self.selected = None;
+    }
+
+    /// Pause and show the slowest frame of the history
+    pub fn select_slowest(&mut self, profiler: &CpuProfiler) {
+        if !self.is_paused() {
+            self.pause(profiler);
+        }
+        self.selected = self.paused.as_deref().and_then(slowest_frame);
+    }
+
+    /// Render the panel
+    pub fn ui(&mut self, ui: &mut egui::Ui, profiler: &CpuProfiler) {
+        ui.heading("🔥 CPU Profiler");
+        ui.separator();
+        ui.label(
+            "Where the CPU spends each frame: waiting for a surface texture, recording the \
+             command encoder, queue.submit and present. Time spent waiting points at the GPU \
+             or the display; time spent recording or submitting is CPU cost.",
+        );
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            if self.is_paused() {
+                if ui.button("▶ Resume").clicked() {
+                    self.resume();
+                }
+            } else if ui.button("⏸ Pause").clicked() {
+                self.pause(profiler);
+            }
+            if ui
+                .button("🐢 Slowest frame")
+                .on_hover_text("Pause and show the slowest frame of the history")
+                .clicked()
+            {
+                self.select_slowest(profiler);
+            }
+            if ui.button("🔄 Reset").clicked() {
+                profiler.clear();
+                self.resume();
+            }
+        });
+
+        let frames = match &self.paused {
+            Some(frames) => frames.clone(),
+            None => profiler.frames(),
+        };
+        if frames.is_empty() {
+            ui.label("No frames recorded yet.");
+            return;
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if let Some(bottleneck) = Bottleneck::classify(&frames) {
+                ui.label(egui::RichText::new(bottleneck.description()).strong());
+            }
+            ui.add_space(5.0);
+
+            ui.label(egui::RichText::new("Frame history").strong());
+            self.render_history(ui, &frames);
+            ui.add_space(10.0);
+
+            if let Some(frame) = selected_frame(&frames, self.selected) {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Frame {} ({})",
+                        frame.frame,
+                        format_ms(frame.duration)
+                    ))
+                    .strong(),
+                );
+                render_flame_graph(ui, frame);
+            }
+            ui.add_space(10.0);
+
+            ui.label(egui::RichText::new("Mean per frame").strong());
+            render_stats(ui, &frames);
+
+            ui.add_space(10.0);
+            #[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+            ui.label(
+                egui::RichText::new(format!(
+                    "The scopes are also sent to puffin; connect puffin_viewer to {}.",
+                    crate::cpu_profiler::PUFFIN_ADDRESS
+                ))
+                .small()
+                .weak(),
+            );
+            #[cfg(not(all(feature = "puffin", not(target_arch = "wasm32"))))]
+            ui.label(
+                egui::RichText::new(
+                    "Build with the puffin feature to inspect the same scopes in puffin_viewer.",
+                )
+                .small()
+                .weak(),
+            );
+        });
+    }
+
+    /// One bar per frame, split into time waiting, recording, submitting
+    /// and the rest; clicking a bar pauses on that frame
+    fn render_history(&mut self, ui: &mut egui::Ui, frames: &[CpuFrame]) {
+        let (response, painter) = ui.allocate_painter(
+            Vec2::new(ui.available_width(), STRIP_HEIGHT),
+            egui::Sense::click(),
+        );
+        let rect = response.rect;
+        painter.rect_filled(rect, 0.0, Color32::from_rgb(30, 30, 35));
+
+        let longest = frames
+            .iter()
+            .map(|f| f.duration)
+            .max()
+            .unwrap_or_default()
+            .as_secs_f32()
+            .max(f32::EPSILON);
+        let bar_width = rect.width() / HISTORY_FRAMES as f32;
+        let shown = selected_frame(frames, self.selected).map(|f| f.frame);
+        let mut hovered = None;
+        for (i, frame) in frames.iter().enumerate() {
+            let left = rect.left() + i as f32 * bar_width;
+            let column = Rect::from_min_max(
+                Pos2::new(left, rect.top()),
+                Pos2::new(left + bar_width, rect.bottom()),
+            );
+            let mut bottom = rect.bottom();
+            let stages = [SCOPE_ACQUIRE, SCOPE_RECORD, SCOPE_SUBMIT, SCOPE_PRESENT]
+                .map(|name| (scope_color(name), frame.top_level(name)));
+            let staged: Duration = stages.iter().map(|(_, d)| *d).sum();
+            let other = (Color32::GRAY, frame.duration.saturating_sub(staged));
+            for (color, duration) in stages.into_iter().chain([other]) {
+                let height = duration.as_secs_f32() / longest * rect.height();
+                painter.rect_filled(
+                    Rect::from_min_max(
+                        Pos2::new(left, bottom - height),
+                        Pos2::new(left + bar_width.max(1.0), bottom),
+                    ),
+                    0.0,
+                    color,
+                );
+                bottom -= height;
+            }
+            if shown == Some(frame.frame) {
+                painter.rect_stroke(
+                    column,
+                    0.0,
+                    egui::Stroke::new(1.0, Color32::WHITE),
+                    egui::epaint::StrokeKind::Inside,
+                );
+            }
+            if response.hover_pos().is_some_and(|pos| column.contains(pos)) {
+                hovered = Some(frame);
+            }
+        }
+
+        if let Some(frame) = hovered {
+            if response.clicked() {
+                if !self.is_paused() {
+                    self.paused = Some(frames.to_vec());
+                }
+                self.selected = Some(frame.frame);
+            }
+            response.on_hover_text(format!(
+                "Frame {}: {}\nAcquire {}, record {}, submit {}, present {}",
+                frame.frame,
+                format_ms(frame.duration),
+                format_ms(frame.top_level(SCOPE_ACQUIRE)),
+                format_ms(frame.top_level(SCOPE_RECORD)),
+                format_ms(frame.top_level(SCOPE_SUBMIT)),
+                format_ms(frame.top_level(SCOPE_PRESENT)),
+            ));
+        }
+    }
+}
+
+/// The frame as the top row and every scope below its parent, scaled to
+/// the panel width
+pub(crate) fn render_flame_graph(ui: &mut egui::Ui, frame: &CpuFrame) {
+    let height = (frame.depth() + 1) as f32 * ROW_HEIGHT;
+    let (response, painter) = ui.allocate_painter(
+        Vec2::new(ui.available_width(), height),
+        egui::Sense::hover(),
+    );
+    let track = response.rect;
+    painter.rect_filled(track, 0.0, Color32::from_rgb(30, 30, 35));
+
+    let label = |bar: Rect, text: String| {
+        if bar.width() >= MIN_LABEL_WIDTH {
+            painter.with_clip_rect(bar.shrink(2.0)).text(
+                Pos2::new(bar.left() + 4.0, bar.center().y),
+                egui::Align2::LEFT_CENTER,
+                text,
+                egui::FontId::proportional(12.0),
+                Color32::BLACK,
+            );
+        }
+    };
+
+    let root = Rect::from_min_size(track.min, Vec2::new(track.width(), ROW_HEIGHT - 2.0));
+    painter.rect_filled(root, 2.0, Color32::from_gray(150));
+    label(root, format!("Frame {}", format_ms(frame.duration)));
+
+    let hover = response.hover_pos();
+    let mut hovered = None;
+    for scope in &frame.scopes {
+        let bar = scope_rect(scope, frame.duration, track);
+        painter.rect_filled(bar, 2.0, scope_color(&scope.name));
+        label(bar, format!("{} {}", scope.name, format_ms(scope.duration)));
+        if hover.is_some_and(|pos| bar.contains(pos)) {
+            hovered = Some(scope);
+        }
+    }
+
+    if let Some(scope) = hovered {
+        response.on_hover_text(format!(
+            "{}\n{} at +{}, {:.0}% of the frame",
+            scope.name,
+            format_ms(scope.duration),
+            format_ms(scope.start),
+            scope.duration.as_secs_f64() / frame.duration.as_secs_f64().max(f64::EPSILON) * 100.0
+        ));
+    }
+}
+
+pub(crate) fn render_stats(ui: &mut egui::Ui, frames: &[CpuFrame]) {
+    egui::Grid::new("cpu_profiler_stats")
+        .num_columns(4)
+        .spacing([12.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new("Scope").strong());
+            ui.label(egui::RichText::new("Mean").strong());
+            ui.label(egui::RichText::new("Max").strong());
+            ui.label(egui::RichText::new("Share").strong());
+            ui.end_row();
+
+            for stats in ScopeStats::from_frames(frames) {
+                ui.horizontal(|ui| {
+                    ui.add_space(stats.depth as f32 * 12.0);
+                    ui.colored_label(scope_color(&stats.name), "■");
+                    ui.label(stats.name.as_ref());
+                });
+                ui.monospace(format_ms(stats.mean));
+                ui.monospace(format_ms(stats.max));
+                ui.monospace(format!("{:.0}%", stats.share * 100.0));
+                ui.end_row();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(number: u64, ms: u64) -> CpuFrame {
+        CpuFrame {
+            frame: number,
+            duration: Duration::from_millis(ms),
+            scopes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_frame_selection() {
+        let frames = vec![frame(3, 10), frame(4, 30), frame(5, 20)];
+        assert_eq!(selected_frame(&frames, None).map(|f| f.frame), Some(5));
+        assert_eq!(selected_frame(&frames, Some(3)).map(|f| f.frame), Some(3));
+        // A frame that left the history falls back to the newest
+        assert_eq!(selected_frame(&frames, Some(1)).map(|f| f.frame), Some(5));
+        assert_eq!(slowest_frame(&frames), Some(4));
+        assert_eq!(selected_frame(&[], None), None);
+    }
+
+    #[test]
+    fn test_scope_rect() {
+        let track = Rect::from_min_size(Pos2::new(10.0, 0.0), Vec2::new(100.0, 100.0));
+        let scope = CpuScope {
+            name: SCOPE_SUBMIT.into(),
+            depth: 1,
+            start: Duration::from_millis(5),
+            duration: Duration::from_millis(2),
+        };
+        let bar = scope_rect(&scope, Duration::from_millis(10), track);
+        assert_eq!(bar.left(), 60.0);
+        assert_eq!(bar.right(), 80.0);
+        assert_eq!(bar.top(), 2.0 * ROW_HEIGHT);
+
+        // Zero-length scopes stay visible
+        let instant = CpuScope {
+            duration: Duration::ZERO,
+            ..scope
+        };
+        assert_eq!(
+            scope_rect(&instant, Duration::from_millis(10), track).width(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_waiting_stages_share_a_color() {
+        assert_eq!(scope_color(SCOPE_ACQUIRE), scope_color(SCOPE_PRESENT));
+        assert_ne!(scope_color(SCOPE_RECORD), scope_color(SCOPE_ACQUIRE));
+        assert_eq!(scope_color("Upload"), scope_color("Upload"));
+    }
+
+    #[test]
+    fn test_pause_and_select_slowest() {
+        let profiler = CpuProfiler::new();
+        let mut panel = CpuProfilerPanel::new();
+        panel.select_slowest(&profiler);
+        assert!(panel.is_paused());
+        assert_eq!(panel.selected, None);
+        panel.resume();
+        assert!(!panel.is_paused());
+    }
+}
@@ -0,0 +1,480 @@
+//! Named atomic counters for lightweight GPU instrumentation
+//!
+//! A shader counts events with `debug_count("fragments shaded")` or
+//! `debug_count_by("rays missed", n)`. [`instrument`] replaces the names
+//! with indices into a shared `array<atomic<u32>>` storage buffer and
+//! appends the buffer declaration and the two helper functions, so the
+//! line numbers of the original source stay valid. [`DebugCounters`] owns
+//! the buffer: it is cleared at the start of every frame and copied into a
+//! readback buffer at the end, and the values arrive a frame or two later
+//! without stalling the GPU. Frames whose readback would have to wait are
+//! skipped rather than blocked on.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+
+/// Number of counters in the debug buffer
+pub const MAX_COUNTERS: usize = 64;
+
+/// Number of frames kept in the history
+pub const HISTORY_FRAMES: usize = 240;
+
+/// Size of the debug buffer in bytes
+const BUFFER_SIZE: u64 = (MAX_COUNTERS * std::mem::size_of::<u32>()) as u64;
+
+/// Calls rewritten by [`instrument`]
+const COUNT_FUNCTIONS: [&str; 2] = ["debug_count_by", "debug_count"];
+
+/// Error from [`instrument`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstrumentError {
+    /// More distinct counter names than [`MAX_COUNTERS`]
+    TooManyCounters(usize),
+    /// A counter call whose first argument is not a string literal
+    MalformedCall { line: usize, message: String },
+}
+
+impl std::fmt::Display for InstrumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstrumentError::TooManyCounters(count) => write!(
+                f,
+                "{} counters used, at most {} are supported",
+                count, MAX_COUNTERS
+            ),
+            InstrumentError::MalformedCall { line, message } => {
+                write!(f, "line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstrumentError {}
+
+/// Shader source with counter names replaced by indices
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstrumentedShader {
+    pub source: String,
+    /// Counter names, indexed like the debug buffer
+    pub counters: Vec<String>,
+}
+
+/// Rewrite the counter calls in `source` and declare the debug buffer
+///
+/// The buffer is declared at `@group(group) @binding(binding)`. Calls
+/// inside comments are rewritten too, which is harmless.
+pub fn instrument(
+    source: &str,
+    group: u32,
+    binding: u32,
+) -> Result<InstrumentedShader, InstrumentError> {
+    let mut counters: Vec<String> = Vec::new();
+    let mut output = String::with_capacity(source.len() + 512);
+    let mut rest = source;
+
+    while let Some((start, function)) = next_call(rest) {
+        let (before, call) = rest.split_at(start);
+        output.push_str(before);
+        output.push_str(function);
+
+        let line = source[..source.len() - call.len()].matches('\n').count() + 1;
+        let malformed = |message: &str| InstrumentError::MalformedCall {
+            line,
+            message: format!("{}: {}", function, message),
+        };
+        let args = call[function.len()..].trim_start();
+        let args = args
+            .strip_prefix('(')
+            .ok_or_else(|| malformed("expected `(`"))?
+            .trim_start();
+        let literal = args
+            .strip_prefix('"')
+            .ok_or_else(|| malformed("the counter name must be a string literal"))?;
+        let end = literal
+            .find(['"', '\n'])
+            .filter(|&end| literal[end..].starts_with('"'))
+            .ok_or_else(|| malformed("unterminated counter name"))?;
+        let name = &literal[..end];
+        if name.trim().is_empty() {
+            return Err(malformed("the counter name is empty"));
+        }
+
+        let index = match counters.iter().position(|counter| counter == name) {
+            Some(index) => index,
+            None => {
+                counters.push(name.to_string());
+                counters.len() - 1
+            }
+        };
+        output.push_str(&format!("({}u", index));
+        rest = &literal[end + 1..];
+    }
+    output.push_str(rest);
+
+    if counters.len() > MAX_COUNTERS {
+        return Err(InstrumentError::TooManyCounters(counters.len()));
+    }
+
+    output.push_str(&format!(
+        "\n// Debug counters, added by the playground
+@group({group}) @binding({binding}) var<storage, read_write> debug_counters: array<atomic<u32>, {MAX_COUNTERS}>;
+
+fn debug_count(counter: u32) {{
+    atomicAdd(&debug_counters[counter], 1u);
+}}
+
+fn debug_count_by(counter: u32, amount: u32) {{
+    atomicAdd(&debug_counters[counter], amount);
+}}
+"
+    ));
+    Ok(InstrumentedShader {
+        source: output,
+        counters,
+    })
+}
+
+/// Byte offset and name of the next counter call in `source`
+fn next_call(source: &str) -> Option<(usize, &'static str)> {
+    let mut offset = 0;
+    while offset < source.len() {
+        let (start, function) = COUNT_FUNCTIONS
+            .iter()
+            .filter_map(|function| {
+                source[offset..]
+                    .find(function)
+                    .map(|start| (offset + start, *function))
+            })
+            .min_by_key(|(start, function)| (*start, std::cmp::Reverse(function.len())))?;
+        let end = start + function.len();
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        let standalone =
+            !source[..start].ends_with(is_ident) && !source[end..].starts_with(is_ident);
+        if standalone {
+            return Some((start, function));
+        }
+        offset = end;
+    }
+    None
+}
+
+/// Summary of one counter over the recorded frames
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CounterStats {
+    pub last: u32,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+}
+
+impl CounterStats {
+    /// Statistics of counter `index` over `frames`, or `None` without frames
+    pub fn from_frames<'a>(
+        frames: impl IntoIterator<Item = &'a Vec<u32>>,
+        index: usize,
+    ) -> Option<Self> {
+        let values: Vec<u32> = frames
+            .into_iter()
+            .map(|frame| frame.get(index).copied().unwrap_or(0))
+            .collect();
+        let last = *values.last()?;
+        Some(Self {
+            last,
+            min: values.iter().copied().min().unwrap_or(0),
+            max: values.iter().copied().max().unwrap_or(0),
+            mean: values.iter().map(|&v| f64::from(v)).sum::<f64>() / values.len() as f64,
+        })
+    }
+}
+
+/// Where the readback buffer is in its copy/map cycle
+enum Readback {
+    Idle,
+    /// The copy is recorded but not yet submitted
+    Copied,
+    /// Mapping was requested; the callback stores whether it succeeded
+    Mapping(Arc<Mutex<Option<bool>>>),
+}
+
+/// The debug buffer and the per-frame counter history
+pub struct DebugCounters {
+    buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    readback: Readback,
+    names: Vec<String>,
+    history: VecDeque<Vec<u32>>,
+    /// Frames whose values were not read back because a readback was
+    /// still in flight
+    skipped_frames: u64,
+}
+
+impl DebugCounters {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Debug Counters"),
+            size: BUFFER_SIZE,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Debug Counters Readback"),
+            size: BUFFER_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            buffer,
+            readback_buffer,
+            readback: Readback::Idle,
+            names: Vec::new(),
+            history: VecDeque::new(),
+            skipped_frames: 0,
+        }
+    }
+
+    /// Layout entry for the debug buffer at `binding`
+    pub fn layout_entry(
+        binding: u32,
+        visibility: wgpu::ShaderStages,
+    ) -> wgpu::BindGroupLayoutEntry {
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: wgpu::BufferSize::new(BUFFER_SIZE),
+            },
+            count: None,
+        }
+    }
+
+    /// Bind group entry for the debug buffer at `binding`
+    pub fn bind_group_entry(&self, binding: u32) -> wgpu::BindGroupEntry<'_> {
+        wgpu::BindGroupEntry {
+            binding,
+            resource: self.buffer.as_entire_binding(),
+        }
+    }
+
+    /// Set the counter names, e.g. from [`InstrumentedShader::counters`]
+    ///
+    /// The history is cleared when the names change.
+    pub fn set_counters(&mut self, names: Vec<String>) {
+        if names != self.names {
+            self.names = names;
+            self.clear_history();
+        }
+    }
+
+    /// Names of the counters, indexed like the values in [`Self::history`]
+    pub fn counters(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Reset all counters to zero; record before the instrumented passes
+    pub fn begin_frame(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.clear_buffer(&self.buffer, 0, None);
+    }
+
+    /// Copy the counters for readback; record after the instrumented passes
+    ///
+    /// Call [`Self::frame_submitted`] once the encoder is submitted.
+    pub fn end_frame(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if matches!(self.readback, Readback::Idle) {
+            encoder.copy_buffer_to_buffer(&self.buffer, 0, &self.readback_buffer, 0, BUFFER_SIZE);
+            self.readback = Readback::Copied;
+        } else {
+            self.skipped_frames += 1;
+        }
+    }
+
+    /// Start reading back the values copied by [`Self::end_frame`]
+    pub fn frame_submitted(&mut self) {
+        if !matches!(self.readback, Readback::Copied) {
+            return;
+        }
+        let mapped = Arc::new(Mutex::new(None));
+        let callback_mapped = mapped.clone();
+        self.readback_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                *callback_mapped.lock().unwrap_or_else(|e| e.into_inner()) = Some(result.is_ok());
+            });
+        self.readback = Readback::Mapping(mapped);
+    }
+
+    /// Collect finished readbacks without blocking
+    ///
+    /// Returns true if a new frame was added to the history.
+    pub fn poll(&mut self, device: &wgpu::Device) -> bool {
+        let Readback::Mapping(mapped) = &self.readback else {
+            return false;
+        };
+        let _ = device.poll(wgpu::PollType::Poll);
+        let Some(success) = *mapped.lock().unwrap_or_else(|e| e.into_inner()) else {
+            return false;
+        };
+        self.readback = Readback::Idle;
+        if !success {
+            log::warn!("Failed to map the debug counter readback buffer");
+            return false;
+        }
+
+        let values: Vec<u32> = {
+            let data = self.readback_buffer.slice(..).get_mapped_range();
+            bytemuck::cast_slice(&data)[..self.names.len()].to_vec()
+        };
+        self.readback_buffer.unmap();
+        if self.history.len() == HISTORY_FRAMES {
+            self.history.pop_front();
+        }
+        self.history.push_back(values);
+        true
+    }
+
+    /// Wait until the pending readback has arrived
+    ///
+    /// Meant for tests and one-off measurements; a frame loop should use
+    /// [`Self::poll`].
+    pub fn wait(&mut self, device: &wgpu::Device) -> bool {
+        if !matches!(self.readback, Readback::Mapping(_)) {
+            return false;
+        }
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+        self.poll(device)
+    }
+
+    /// Counter values per frame, oldest first
+    pub fn history(&self) -> &VecDeque<Vec<u32>> {
+        &self.history
+    }
+
+    /// Values of the most recent frame that was read back
+    pub fn latest(&self) -> Option<&[u32]> {
+        self.history.back().map(Vec::as_slice)
+    }
+
+    /// Statistics of counter `index` over the history
+    pub fn stats(&self, index: usize) -> Option<CounterStats> {
+        CounterStats::from_frames(&self.history, index)
+    }
+
+    /// Frames that were not read back because the previous readback was
+    /// still in flight
+    pub fn skipped_frames(&self) -> u64 {
+        self.skipped_frames
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.skipped_frames = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instrument_assigns_indices() {
+        let source = r#"
+fn shade() {
+    debug_count("fragments shaded");
+    debug_count_by("rays missed", 2u);
+    debug_count( "fragments shaded" );
+}
+"#;
+        let shader = instrument(source, 0, 1).unwrap();
+        assert_eq!(shader.counters, vec!["fragments shaded", "rays missed"]);
+        assert!(shader.source.contains("debug_count(0u);"));
+        assert!(shader.source.contains("debug_count_by(1u, 2u);"));
+        assert!(shader.source.contains("debug_count(0u );"));
+        assert!(shader
+            .source
+            .contains("@group(0) @binding(1) var<storage, read_write> debug_counters"));
+    }
+
+    #[test]
+    fn test_instrument_keeps_line_numbers() {
+        let source = "fn a() {\n    debug_count(\"a\");\n}\nfn b() {}\n";
+        let shader = instrument(source, 0, 0).unwrap();
+        let original: Vec<&str> = source.lines().collect();
+        let instrumented: Vec<&str> = shader.source.lines().collect();
+        assert_eq!(instrumented[0], original[0]);
+        assert_eq!(instrumented[3], original[3]);
+    }
+
+    #[test]
+    fn test_instrument_ignores_other_identifiers() {
+        let source = "fn my_debug_count(x: u32) {}\nfn f() { my_debug_count(1u); }\n";
+        let shader = instrument(source, 0, 0).unwrap();
+        assert!(shader.counters.is_empty());
+        assert!(shader.source.starts_with(source));
+    }
+
+    #[test]
+    fn test_instrument_rejects_non_literal_names() {
+        let error = instrument("fn f() {\n    debug_count(3u);\n}", 0, 0).unwrap_err();
+        assert!(matches!(
+            error,
+            InstrumentError::MalformedCall { line: 2, .. }
+        ));
+
+        let error = instrument("fn f() { debug_count(\"open); }", 0, 0).unwrap_err();
+        assert!(error.to_string().contains("unterminated"));
+    }
+
+    #[test]
+    fn test_instrument_counter_limit() {
+        let source: String = (0..=MAX_COUNTERS)
+            .map(|i| format!("debug_count(\"c{}\");\n", i))
+            .collect();
+        assert_eq!(
+            instrument(&source, 0, 0),
+            Err(InstrumentError::TooManyCounters(MAX_COUNTERS + 1))
+        );
+    }
+
+    #[test]
+    fn test_instrumented_shader_validates() {
+        let source = r#"
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    debug_count("invocations");
+    if id.x % 2u == 0u {
+        debug_count_by("even", 2u);
+    }
+}
+"#;
+        let shader = instrument(source, 0, 0).unwrap();
+        let module = naga::front::wgsl::parse_str(&shader.source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_counter_stats() {
+        let frames = vec![vec![1, 10], vec![3, 20], vec![2]];
+        let stats = CounterStats::from_frames(&frames, 0).unwrap();
+        assert_eq!((stats.last, stats.min, stats.max), (2, 1, 3));
+        assert!((stats.mean - 2.0).abs() < 1e-9);
+
+        // Frames recorded before a counter existed count as zero
+        let stats = CounterStats::from_frames(&frames, 1).unwrap();
+        assert_eq!((stats.last, stats.min, stats.max), (0, 0, 20));
+
+        assert!(CounterStats::from_frames(&Vec::new(), 0).is_none());
+    }
+}
@@ -0,0 +1,479 @@
+use std::collections::BTreeSet;
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::debug_counters::{instrument, DebugCounters, HISTORY_FRAMES};
+use crate::device_recovery::ResourceRecreate;
+use crate::operation_errors::OperationErrors;
+
+/// Bind group of the frame uniform and the debug buffer
+const GROUP: u32 = 0;
+/// Binding of the frame uniform (frame index, invocation count)
+const FRAME_BINDING: u32 = 0;
+/// Binding of the debug buffer
+const COUNTERS_BINDING: u32 = 1;
+
+/// Invocations per workgroup in the default shader
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Operation name of the pipeline build in [`OperationErrors`]
+const BUILD_PIPELINE: &str = "build_pipeline";
+
+const DEFAULT_SHADER: &str = r#"// Count events with debug_count("name") or debug_count_by("name", n).
+// The debug buffer is bound automatically at @group(0) @binding(1).
+
+struct Frame {
+    index: u32,
+    invocations: u32,
+}
+
+@group(0) @binding(0) var<uniform> frame: Frame;
+
+fn hash(value: u32) -> u32 {
+    var x = value * 747796405u + 2891336453u;
+    x = ((x >> ((x >> 28u) + 4u)) ^ x) * 277803737u;
+    return (x >> 22u) ^ x;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    debug_count("rays cast");
+
+    // A random ray towards a sphere whose radius pulses over time
+    let seed = hash(id.x ^ hash(frame.index));
+    let aim = vec2<f32>(f32(seed & 0xffffu), f32(seed >> 16u)) / 65535.0 * 2.0 - 1.0;
+    let radius = 0.6 + 0.3 * sin(f32(frame.index) * 0.05);
+    if length(aim) > radius {
+        debug_count("rays missed");
+        return;
+    }
+
+    debug_count("rays hit");
+    debug_count_by("bounces", 1u + hash(seed) % 4u);
+}
+"#;
+
+/// GPU objects of the instrumented shader
+struct CounterPipeline {
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    frame_buffer: wgpu::Buffer,
+}
+
+/// UI panel that runs an instrumented compute shader every frame and
+/// charts its debug counters
+pub struct DebugCountersPanel {
+    source: String,
+    workgroups: u32,
+    running: bool,
+    step_requested: bool,
+    frame_index: u32,
+    /// Counters left out of the chart
+    hidden: BTreeSet<String>,
+    counters: Option<DebugCounters>,
+    pipeline: Option<CounterPipeline>,
+    needs_rebuild: bool,
+    /// Error from instrumenting the source
+    instrument_error: Option<String>,
+    errors: OperationErrors,
+}
+
+impl Default for DebugCountersPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DebugCountersPanel {
+    pub fn new() -> Self {
+        Self {
+            source: DEFAULT_SHADER.to_string(),
+            workgroups: 64,
+            running: false,
+            step_requested: false,
+            frame_index: 0,
+            hidden: BTreeSet::new(),
+            counters: None,
+            pipeline: None,
+            needs_rebuild: true,
+            instrument_error: None,
+            errors: OperationErrors::new(),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Replace the shader; it is rebuilt before the next frame
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = source.into();
+        self.needs_rebuild = true;
+    }
+
+    pub fn set_workgroups(&mut self, workgroups: u32) {
+        self.workgroups = workgroups.max(1);
+    }
+
+    pub fn set_running(&mut self, running: bool) {
+        self.running = running;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// The counters and their history, once a frame has run
+    pub fn counters(&self) -> Option<&DebugCounters> {
+        self.counters.as_ref()
+    }
+
+    /// Why the last build failed, if it did
+    pub fn build_error(&self) -> Option<String> {
+        self.instrument_error
+            .clone()
+            .or_else(|| self.errors.get(BUILD_PIPELINE).map(|e| e.message))
+    }
+
+    /// Instrument the shader and create its pipeline
+    fn build(&mut self, device: &wgpu::Device) {
+        self.needs_rebuild = false;
+        self.pipeline = None;
+        let shader = match instrument(&self.source, GROUP, COUNTERS_BINDING) {
+            Ok(shader) => shader,
+            Err(e) => {
+                self.instrument_error = Some(e.to_string());
+                return;
+            }
+        };
+        self.instrument_error = None;
+
+        let counters = self
+            .counters
+            .get_or_insert_with(|| DebugCounters::new(device));
+        counters.set_counters(shader.counters);
+
+        let tracker = ApiCoverageTracker::global();
+        let pipeline = self.errors.scoped(device, BUILD_PIPELINE, || {
+            tracker.record(ApiCategory::Shader, "create_shader_module");
+            let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Debug Counters Shader"),
+                source: wgpu::ShaderSource::Wgsl(shader.source.into()),
+            });
+
+            tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+            let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Debug Counters Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: FRAME_BINDING,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    DebugCounters::layout_entry(COUNTERS_BINDING, wgpu::ShaderStages::COMPUTE),
+                ],
+            });
+
+            tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Debug Counters Pipeline Layout"),
+                bind_group_layouts: &[Some(&layout)],
+                immediate_size: 0,
+            });
+
+            tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+            let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Debug Counters Pipeline"),
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: None,
+                compilation_options: Default::default(),
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            });
+
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            let frame_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Debug Counters Frame"),
+                size: 16,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            tracker.record(ApiCategory::BindGroup, "create_bind_group");
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Debug Counters Bind Group"),
+                layout: &layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: FRAME_BINDING,
+                        resource: frame_buffer.as_entire_binding(),
+                    },
+                    counters.bind_group_entry(COUNTERS_BINDING),
+                ],
+            });
+
+            CounterPipeline {
+                pipeline,
+                bind_group,
+                frame_buffer,
+            }
+        });
+
+        if !self.errors.has_error(BUILD_PIPELINE) {
+            self.pipeline = Some(pipeline);
+        }
+    }
+
+    /// Dispatch the shader once and collect finished readbacks
+    ///
+    /// Counter values show up in the history a frame or two later.
+    pub fn run_frame(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.needs_rebuild {
+            self.build(device);
+        }
+        let (Some(pipeline), Some(counters)) = (&self.pipeline, &mut self.counters) else {
+            return;
+        };
+
+        let invocations = self.workgroups.saturating_mul(WORKGROUP_SIZE);
+        queue.write_buffer(
+            &pipeline.frame_buffer,
+            0,
+            bytemuck::cast_slice(&[self.frame_index, invocations, 0, 0]),
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Debug Counters Encoder"),
+        });
+        counters.begin_frame(&mut encoder);
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Debug Counters Pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&pipeline.pipeline);
+            pass.set_bind_group(GROUP, &pipeline.bind_group, &[]);
+            pass.dispatch_workgroups(self.workgroups, 1, 1);
+        }
+        counters.end_frame(&mut encoder);
+        queue.submit(std::iter::once(encoder.finish()));
+        counters.frame_submitted();
+        counters.poll(device);
+
+        self.frame_index = self.frame_index.wrapping_add(1);
+    }
+
+    /// Reset the frame index and the recorded history
+    pub fn reset(&mut self) {
+        self.frame_index = 0;
+        if let Some(counters) = &mut self.counters {
+            counters.clear_history();
+        }
+    }
+
+    /// Render the panel, running a frame when the shader is running or a
+    /// single step was requested
+    pub fn ui(&mut self, ui: &mut egui::Ui, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.running || std::mem::take(&mut self.step_requested) {
+            self.run_frame(device, queue);
+        }
+        if self.running {
+            ui.ctx().request_repaint();
+        }
+
+        ui.heading("🔢 Debug Counters");
+        ui.separator();
+        ui.label(
+            "Count events from a shader with debug_count(\"name\") and watch the totals per frame.",
+        );
+        ui.add_space(10.0);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.label("Shader Source:");
+            let response = ui.add(
+                egui::TextEdit::multiline(&mut self.source)
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(14),
+            );
+            if response.changed() {
+                self.needs_rebuild = true;
+            }
+            if let Some(error) = &self.instrument_error {
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+            }
+            self.errors.ui(ui, BUILD_PIPELINE);
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                let label = if self.running { "⏸ Pause" } else { "▶ Run" };
+                if ui.button(label).clicked() {
+                    self.running = !self.running;
+                }
+                if ui
+                    .add_enabled(!self.running, egui::Button::new("⏭ Step"))
+                    .clicked()
+                {
+                    self.step_requested = true;
+                    ui.ctx().request_repaint();
+                }
+                if ui.button("🔄 Reset").clicked() {
+                    self.reset();
+                }
+                ui.separator();
+                ui.label("Workgroups:");
+                ui.add(egui::DragValue::new(&mut self.workgroups).range(1..=65535));
+                ui.label(format!(
+                    "({} invocations)",
+                    self.workgroups.saturating_mul(WORKGROUP_SIZE)
+                ));
+            });
+            ui.label(format!("Frame {}", self.frame_index));
+            ui.add_space(10.0);
+
+            self.render_counters(ui);
+        });
+    }
+
+    fn render_counters(&mut self, ui: &mut egui::Ui) {
+        let Some(counters) = &self.counters else {
+            ui.label("Run the shader to collect counters.");
+            return;
+        };
+        if counters.counters().is_empty() {
+            ui.label("The shader does not call debug_count.");
+            return;
+        }
+
+        egui::Grid::new("debug_counter_stats")
+            .num_columns(6)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label(egui::RichText::new("Counter").strong());
+                ui.label(egui::RichText::new("Last").strong());
+                ui.label(egui::RichText::new("Min").strong());
+                ui.label(egui::RichText::new("Max").strong());
+                ui.label(egui::RichText::new("Mean").strong());
+                ui.end_row();
+
+                for (index, name) in counters.counters().iter().enumerate() {
+                    let mut shown = !self.hidden.contains(name);
+                    if ui
+                        .checkbox(&mut shown, "")
+                        .on_hover_text("Show in chart")
+                        .changed()
+                    {
+                        if shown {
+                            self.hidden.remove(name);
+                        } else {
+                            self.hidden.insert(name.clone());
+                        }
+                    }
+                    ui.monospace(name);
+                    match counters.stats(index) {
+                        Some(stats) => {
+                            ui.monospace(stats.last.to_string());
+                            ui.monospace(stats.min.to_string());
+                            ui.monospace(stats.max.to_string());
+                            ui.monospace(format!("{:.1}", stats.mean));
+                        }
+                        None => {
+                            for _ in 0..4 {
+                                ui.label("-");
+                            }
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if counters.skipped_frames() > 0 {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} frame(s) not read back while a readback was in flight",
+                    counters.skipped_frames()
+                ))
+                .small()
+                .weak(),
+            );
+        }
+        ui.add_space(10.0);
+
+        let history = counters.history();
+        let lines: Vec<egui_plot::Line> = counters
+            .counters()
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| !self.hidden.contains(*name))
+            .map(|(index, name)| {
+                let points: egui_plot::PlotPoints = history
+                    .iter()
+                    .enumerate()
+                    .map(|(frame, values)| {
+                        [frame as f64, values.get(index).copied().unwrap_or(0) as f64]
+                    })
+                    .collect();
+                egui_plot::Line::new(name.as_str(), points)
+            })
+            .collect();
+
+        egui_plot::Plot::new("debug_counters_plot")
+            .height(220.0)
+            .legend(egui_plot::Legend::default())
+            .include_x(HISTORY_FRAMES as f64)
+            .include_y(0.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                for line in lines {
+                    plot_ui.line(line);
+                }
+            });
+    }
+}
+
+impl ResourceRecreate for DebugCountersPanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // The buffers and pipeline belong to the old device; they are
+        // rebuilt before the next frame
+        self.pipeline = None;
+        self.counters = None;
+        self.needs_rebuild = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_shader_instruments() {
+        let shader = instrument(DEFAULT_SHADER, GROUP, COUNTERS_BINDING).unwrap();
+        assert_eq!(
+            shader.counters,
+            vec!["rays cast", "rays missed", "rays hit", "bounces"]
+        );
+        let module = naga::front::wgsl::parse_str(&shader.source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_set_source_requests_rebuild() {
+        let mut panel = DebugCountersPanel::new();
+        panel.needs_rebuild = false;
+        panel.set_source("@compute @workgroup_size(1) fn main() {}");
+        assert!(panel.needs_rebuild);
+        assert!(panel.build_error().is_none());
+    }
+}
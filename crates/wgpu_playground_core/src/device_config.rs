@@ -1,7 +1,12 @@
 use wgpu::{Features, Limits};
 
+use crate::state::DeviceConfigState;
+
+/// Label of every device the playground creates
+pub const DEVICE_LABEL: &str = "WebGPU Playground Device";
+
 /// Configuration for device creation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DeviceConfig {
     pub features: Features,
     pub limits: Limits,
@@ -25,11 +30,19 @@ impl DeviceConfig {
     /// Create configuration from adapter capabilities
     pub fn from_adapter(adapter: &wgpu::Adapter) -> Self {
         Self {
-            features: adapter.features(),
+            features: adapter.features() & Self::selectable_features(),
             limits: adapter.limits(),
         }
     }
 
+    /// Features that can be requested from the panel
+    ///
+    /// Experimental features need an unsafe opt-in on the device descriptor
+    /// and are left out.
+    pub fn selectable_features() -> Features {
+        Features::all() - Features::all_experimental_mask()
+    }
+
     /// Set a specific feature
     pub fn set_feature(&mut self, feature: Features, enabled: bool) {
         if enabled {
@@ -43,22 +56,225 @@ impl DeviceConfig {
     pub fn has_feature(&self, feature: Features) -> bool {
         self.features.contains(feature)
     }
+
+    /// Descriptor requesting a device with this configuration
+    pub fn descriptor(&self) -> wgpu::DeviceDescriptor<'static> {
+        wgpu::DeviceDescriptor {
+            required_features: self.features,
+            required_limits: self.limits.clone(),
+            label: Some(DEVICE_LABEL),
+            memory_hints: Default::default(),
+            experimental_features: Default::default(),
+            trace: wgpu::Trace::Off,
+        }
+    }
+
+    /// Drop features and lower limits the adapter does not support
+    pub fn restricted_to(&self, features: Features, limits: &Limits) -> Self {
+        Self {
+            features: self.features & features & Self::selectable_features(),
+            limits: self.limits.clone().or_worse_values_from(limits),
+        }
+    }
+
+    /// Describe every selection the adapter does not support
+    ///
+    /// An empty list means a device can be requested with this configuration.
+    pub fn unsupported(&self, features: Features, limits: &Limits) -> Vec<String> {
+        let mut problems: Vec<String> = (self.features - features)
+            .iter_names()
+            .map(|(name, _)| format!("feature {} is not supported", name))
+            .collect();
+        self.limits
+            .check_limits_with_fail_fn(limits, false, |name, requested, allowed| {
+                problems.push(format!(
+                    "limit {} = {} exceeds the adapter's {}",
+                    name, requested, allowed
+                ));
+            });
+        problems
+    }
+
+    /// Convert to the serializable form stored in saved state
+    pub fn to_state(&self) -> DeviceConfigState {
+        let defaults = Limits::default();
+        DeviceConfigState {
+            features: self
+                .features
+                .iter_names()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            limits: LIMIT_FIELDS
+                .iter()
+                .filter(|field| field.get(&self.limits) != field.get(&defaults))
+                .map(|field| (field.name.to_string(), field.get(&self.limits)))
+                .collect(),
+        }
+    }
+
+    /// Rebuild a configuration from saved state
+    ///
+    /// Unknown feature and limit names are skipped with a warning.
+    pub fn from_state(state: &DeviceConfigState) -> Self {
+        let mut config = Self::default();
+        for name in &state.features {
+            match Features::from_name(name) {
+                Some(feature) => config.features.insert(feature),
+                None => log::warn!("Ignoring unknown device feature {}", name),
+            }
+        }
+        for (name, &value) in &state.limits {
+            match LIMIT_FIELDS.iter().find(|field| field.name == name) {
+                Some(field) => field.set(&mut config.limits, value),
+                None => log::warn!("Ignoring unknown device limit {}", name),
+            }
+        }
+        config
+    }
 }
 
+/// A `wgpu::Limits` field that can be edited in the panel
+pub struct LimitField {
+    /// Field name in `wgpu::Limits`
+    pub name: &'static str,
+    pub label: &'static str,
+    get: fn(&Limits) -> u64,
+    set: fn(&mut Limits, u64),
+}
+
+impl LimitField {
+    pub fn get(&self, limits: &Limits) -> u64 {
+        (self.get)(limits)
+    }
+
+    pub fn set(&self, limits: &mut Limits, value: u64) {
+        (self.set)(limits, value)
+    }
+}
+
+macro_rules! limit_field {
+    ($label:literal, $field:ident) => {
+        LimitField {
+            name: stringify!($field),
+            label: $label,
+            get: |limits| u64::from(limits.$field),
+            set: |limits, value| limits.$field = value.try_into().unwrap_or(limits.$field),
+        }
+    };
+}
+
+/// Maximum limits of the WebGPU spec, in the order they are shown
+pub const LIMIT_FIELDS: &[LimitField] = &[
+    limit_field!("Max Texture Dimension 1D", max_texture_dimension_1d),
+    limit_field!("Max Texture Dimension 2D", max_texture_dimension_2d),
+    limit_field!("Max Texture Dimension 3D", max_texture_dimension_3d),
+    limit_field!("Max Texture Array Layers", max_texture_array_layers),
+    limit_field!("Max Bind Groups", max_bind_groups),
+    limit_field!("Max Bindings Per Bind Group", max_bindings_per_bind_group),
+    limit_field!(
+        "Max Dynamic Uniform Buffers Per Pipeline Layout",
+        max_dynamic_uniform_buffers_per_pipeline_layout
+    ),
+    limit_field!(
+        "Max Dynamic Storage Buffers Per Pipeline Layout",
+        max_dynamic_storage_buffers_per_pipeline_layout
+    ),
+    limit_field!(
+        "Max Sampled Textures Per Shader Stage",
+        max_sampled_textures_per_shader_stage
+    ),
+    limit_field!(
+        "Max Samplers Per Shader Stage",
+        max_samplers_per_shader_stage
+    ),
+    limit_field!(
+        "Max Storage Buffers Per Shader Stage",
+        max_storage_buffers_per_shader_stage
+    ),
+    limit_field!(
+        "Max Storage Textures Per Shader Stage",
+        max_storage_textures_per_shader_stage
+    ),
+    limit_field!(
+        "Max Uniform Buffers Per Shader Stage",
+        max_uniform_buffers_per_shader_stage
+    ),
+    limit_field!(
+        "Max Uniform Buffer Binding Size",
+        max_uniform_buffer_binding_size
+    ),
+    limit_field!(
+        "Max Storage Buffer Binding Size",
+        max_storage_buffer_binding_size
+    ),
+    limit_field!("Max Buffer Size", max_buffer_size),
+    limit_field!("Max Vertex Buffers", max_vertex_buffers),
+    limit_field!("Max Vertex Attributes", max_vertex_attributes),
+    limit_field!(
+        "Max Vertex Buffer Array Stride",
+        max_vertex_buffer_array_stride
+    ),
+    limit_field!(
+        "Max Inter-Stage Shader Variables",
+        max_inter_stage_shader_variables
+    ),
+    limit_field!("Max Color Attachments", max_color_attachments),
+    limit_field!(
+        "Max Color Attachment Bytes Per Sample",
+        max_color_attachment_bytes_per_sample
+    ),
+    limit_field!(
+        "Max Compute Workgroup Storage Size",
+        max_compute_workgroup_storage_size
+    ),
+    limit_field!(
+        "Max Compute Invocations Per Workgroup",
+        max_compute_invocations_per_workgroup
+    ),
+    limit_field!("Max Compute Workgroup Size X", max_compute_workgroup_size_x),
+    limit_field!("Max Compute Workgroup Size Y", max_compute_workgroup_size_y),
+    limit_field!("Max Compute Workgroup Size Z", max_compute_workgroup_size_z),
+    limit_field!(
+        "Max Compute Workgroups Per Dimension",
+        max_compute_workgroups_per_dimension
+    ),
+];
+
 /// UI panel for configuring device features and limits before device creation
+///
+/// The panel edits a pending configuration. "Apply" asks the application to
+/// replace the device with one created from it; the configuration the
+/// current device was created with is kept to show what would change.
 pub struct DeviceConfigPanel {
     config: DeviceConfig,
+    /// Configuration of the current device
+    active: DeviceConfig,
     adapter_features: Features,
     adapter_limits: Limits,
+    feature_filter: String,
+    show_unsupported: bool,
+    apply_requested: bool,
+    /// Outcome of the last apply
+    status: Option<Result<String, String>>,
 }
 
 impl DeviceConfigPanel {
     /// Create a new device configuration panel
     pub fn new(adapter: &wgpu::Adapter) -> Self {
+        Self::with_capabilities(adapter.features(), adapter.limits())
+    }
+
+    /// Create a panel for an adapter with the given capabilities
+    pub fn with_capabilities(adapter_features: Features, adapter_limits: Limits) -> Self {
         Self {
             config: DeviceConfig::default(),
-            adapter_features: adapter.features(),
-            adapter_limits: adapter.limits(),
+            active: DeviceConfig::default(),
+            adapter_features,
+            adapter_limits,
+            feature_filter: String::new(),
+            show_unsupported: true,
+            apply_requested: false,
+            status: None,
         }
     }
 
@@ -67,25 +283,74 @@ impl DeviceConfigPanel {
         &self.config
     }
 
+    /// Replace the pending configuration, e.g. from saved state
+    pub fn set_config(&mut self, config: DeviceConfig) {
+        self.config = config;
+    }
+
+    /// Configuration the current device was created with
+    pub fn active_config(&self) -> &DeviceConfig {
+        &self.active
+    }
+
+    /// Record the configuration of a newly created device
+    ///
+    /// Also resets the pending configuration to it.
+    pub fn set_active(&mut self, config: DeviceConfig) {
+        self.config = config.clone();
+        self.active = config;
+    }
+
+    /// Update the capabilities after switching to another adapter
+    pub fn set_adapter(&mut self, adapter: &wgpu::Adapter) {
+        self.adapter_features = adapter.features();
+        self.adapter_limits = adapter.limits();
+    }
+
+    /// Whether the pending configuration differs from the current device
+    pub fn has_changes(&self) -> bool {
+        self.config != self.active
+    }
+
+    /// Problems that prevent requesting a device with the pending configuration
+    pub fn unsupported(&self) -> Vec<String> {
+        self.config
+            .unsupported(self.adapter_features, &self.adapter_limits)
+    }
+
+    /// Returns the configuration to create a new device with, once per click on "Apply"
+    pub fn take_apply_request(&mut self) -> Option<DeviceConfig> {
+        std::mem::take(&mut self.apply_requested).then(|| self.config.clone())
+    }
+
+    /// Report whether the device was recreated with the requested configuration
+    ///
+    /// `result` holds the new adapter name, or why no device could be
+    /// requested; on failure the current device is kept.
+    pub fn finish_apply(&mut self, result: Result<String, String>) {
+        if result.is_ok() {
+            self.active = self.config.clone();
+        }
+        self.status = Some(result);
+    }
+
     /// Render the configuration UI
     pub fn ui(&mut self, ui: &mut egui::Ui) {
         egui::ScrollArea::vertical().show(ui, |ui| {
             ui.heading("⚙️ Device Configuration");
-            ui.label("Configure features and limits for device creation.");
-
-            ui.add_space(5.0);
-            ui.colored_label(
-                egui::Color32::from_rgb(255, 200, 100),
-                "ℹ️ Note: This panel shows available features and limits. In the current version, \
-                the device is created at startup with default settings. This UI can be used to \
-                explore what features and limits your adapter supports.",
+            ui.label(
+                "Choose the features and limits the GPU device is created with. \
+                The selection is saved with the playground state and used at startup.",
             );
             ui.add_space(10.0);
 
+            self.render_apply_ui(ui);
+            ui.add_space(20.0);
+
             // Features section
-            ui.heading("Available Features");
+            ui.heading("Features");
             ui.separator();
-            ui.label("Enable or disable WebGPU features:");
+            ui.label("Features the adapter does not support are greyed out:");
             ui.add_space(5.0);
 
             self.render_features_ui(ui);
@@ -101,66 +366,99 @@ impl DeviceConfigPanel {
         });
     }
 
+    fn render_apply_ui(&mut self, ui: &mut egui::Ui) {
+        let problems = self.unsupported();
+        let changed = self.has_changes();
+
+        ui.horizontal(|ui| {
+            let apply = ui
+                .add_enabled(
+                    changed && problems.is_empty(),
+                    egui::Button::new("♻ Apply (recreate device)"),
+                )
+                .on_hover_text(
+                    "Request a new device with this configuration; \
+                    GPU resources are rebuilt on it",
+                );
+            if apply.clicked() {
+                self.apply_requested = true;
+            }
+            if ui
+                .add_enabled(changed, egui::Button::new("↺ Revert"))
+                .on_hover_text("Go back to the configuration of the current device")
+                .clicked()
+            {
+                self.config = self.active.clone();
+            }
+        });
+
+        if changed {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 200, 100),
+                "ℹ️ The current device was created with a different configuration.",
+            );
+        }
+        for problem in &problems {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 100, 100),
+                format!("✗ {}", problem),
+            );
+        }
+        match &self.status {
+            Some(Ok(adapter_name)) => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(100, 200, 100),
+                    format!("✓ Device recreated on {}", adapter_name),
+                );
+            }
+            Some(Err(e)) => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 100, 100),
+                    format!("✗ Device not recreated: {}", e),
+                );
+            }
+            None => {}
+        }
+    }
+
     fn render_features_ui(&mut self, ui: &mut egui::Ui) {
+        let supported = self.adapter_features & DeviceConfig::selectable_features();
+        ui.label(format!(
+            "{} of {} features supported, {} enabled",
+            supported.iter().count(),
+            DeviceConfig::selectable_features().iter().count(),
+            self.config.features.iter().count()
+        ));
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.text_edit_singleline(&mut self.feature_filter);
+            ui.checkbox(&mut self.show_unsupported, "Show unsupported");
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Enable All Supported").clicked() {
+                self.config.features = supported;
+            }
+            if ui.button("Disable All").clicked() {
+                self.config.features = Features::empty();
+            }
+        });
+        ui.add_space(5.0);
+
+        let filter = self.feature_filter.to_lowercase();
         egui::Grid::new("features_grid")
             .num_columns(2)
             .spacing([10.0, 4.0])
             .striped(true)
             .show(ui, |ui| {
-                // List all available features
-                self.render_feature_checkbox(
-                    ui,
-                    "Depth Clip Control",
-                    Features::DEPTH_CLIP_CONTROL,
-                );
-                self.render_feature_checkbox(
-                    ui,
-                    "Depth32Float Stencil8",
-                    Features::DEPTH32FLOAT_STENCIL8,
-                );
-                self.render_feature_checkbox(ui, "Timestamp Query", Features::TIMESTAMP_QUERY);
-                self.render_feature_checkbox(
-                    ui,
-                    "Pipeline Statistics Query",
-                    Features::PIPELINE_STATISTICS_QUERY,
-                );
-                self.render_feature_checkbox(
-                    ui,
-                    "Texture Compression BC",
-                    Features::TEXTURE_COMPRESSION_BC,
-                );
-                self.render_feature_checkbox(
-                    ui,
-                    "Texture Compression ETC2",
-                    Features::TEXTURE_COMPRESSION_ETC2,
-                );
-                self.render_feature_checkbox(
-                    ui,
-                    "Texture Compression ASTC",
-                    Features::TEXTURE_COMPRESSION_ASTC,
-                );
-                self.render_feature_checkbox(
-                    ui,
-                    "Indirect First Instance",
-                    Features::INDIRECT_FIRST_INSTANCE,
-                );
-                self.render_feature_checkbox(ui, "Shader F16", Features::SHADER_F16);
-                self.render_feature_checkbox(
-                    ui,
-                    "RG11B10UFloat Renderable",
-                    Features::RG11B10UFLOAT_RENDERABLE,
-                );
-                self.render_feature_checkbox(ui, "BGRA8UnormStorage", Features::BGRA8UNORM_STORAGE);
-                self.render_feature_checkbox(
-                    ui,
-                    "Float32 Filterable",
-                    Features::FLOAT32_FILTERABLE,
-                );
-                self.render_feature_checkbox(
-                    ui,
-                    "Shader Primitive Index",
-                    Features::PRIMITIVE_INDEX,
-                );
+                for (name, feature) in DeviceConfig::selectable_features().iter_names() {
+                    if !filter.is_empty() && !name.to_lowercase().contains(&filter) {
+                        continue;
+                    }
+                    if !self.show_unsupported && !self.adapter_features.contains(feature) {
+                        continue;
+                    }
+                    self.render_feature_checkbox(ui, name, feature);
+                }
             });
     }
 
@@ -169,7 +467,12 @@ impl DeviceConfigPanel {
         let mut enabled = self.config.has_feature(feature);
 
         ui.add_enabled_ui(adapter_supports, |ui| {
-            if ui.checkbox(&mut enabled, label).changed() {
+            let checkbox = ui.checkbox(&mut enabled, label);
+            let checkbox = match feature.as_str() {
+                Some(spec_name) => checkbox.on_hover_text(spec_name),
+                None => checkbox,
+            };
+            if checkbox.changed() {
                 self.config.set_feature(feature, enabled);
             }
         });
@@ -190,150 +493,13 @@ impl DeviceConfigPanel {
         let config_limits = &mut self.config.limits;
 
         egui::Grid::new("limits_grid")
-            .num_columns(2)
+            .num_columns(3)
             .spacing([10.0, 4.0])
             .striped(true)
             .show(ui, |ui| {
-                // Texture limits
-                Self::render_limit_u32(
-                    ui,
-                    "Max Texture Dimension 1D",
-                    &mut config_limits.max_texture_dimension_1d,
-                    adapter_limits.max_texture_dimension_1d,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Texture Dimension 2D",
-                    &mut config_limits.max_texture_dimension_2d,
-                    adapter_limits.max_texture_dimension_2d,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Texture Dimension 3D",
-                    &mut config_limits.max_texture_dimension_3d,
-                    adapter_limits.max_texture_dimension_3d,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Texture Array Layers",
-                    &mut config_limits.max_texture_array_layers,
-                    adapter_limits.max_texture_array_layers,
-                    1,
-                );
-
-                // Bind group limits
-                Self::render_limit_u32(
-                    ui,
-                    "Max Bind Groups",
-                    &mut config_limits.max_bind_groups,
-                    adapter_limits.max_bind_groups,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Bindings Per Bind Group",
-                    &mut config_limits.max_bindings_per_bind_group,
-                    adapter_limits.max_bindings_per_bind_group,
-                    1,
-                );
-
-                // Buffer limits
-                Self::render_limit_u64(
-                    ui,
-                    "Max Uniform Buffer Binding Size",
-                    &mut config_limits.max_uniform_buffer_binding_size,
-                    adapter_limits.max_uniform_buffer_binding_size,
-                    1,
-                );
-
-                Self::render_limit_u64(
-                    ui,
-                    "Max Storage Buffer Binding Size",
-                    &mut config_limits.max_storage_buffer_binding_size,
-                    adapter_limits.max_storage_buffer_binding_size,
-                    1,
-                );
-
-                Self::render_limit_u64(
-                    ui,
-                    "Max Buffer Size",
-                    &mut config_limits.max_buffer_size,
-                    adapter_limits.max_buffer_size,
-                    1,
-                );
-
-                // Vertex limits
-                Self::render_limit_u32(
-                    ui,
-                    "Max Vertex Buffers",
-                    &mut config_limits.max_vertex_buffers,
-                    adapter_limits.max_vertex_buffers,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Vertex Attributes",
-                    &mut config_limits.max_vertex_attributes,
-                    adapter_limits.max_vertex_attributes,
-                    1,
-                );
-
-                // Compute limits
-                Self::render_limit_u32(
-                    ui,
-                    "Max Compute Workgroup Size X",
-                    &mut config_limits.max_compute_workgroup_size_x,
-                    adapter_limits.max_compute_workgroup_size_x,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Compute Workgroup Size Y",
-                    &mut config_limits.max_compute_workgroup_size_y,
-                    adapter_limits.max_compute_workgroup_size_y,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Compute Workgroup Size Z",
-                    &mut config_limits.max_compute_workgroup_size_z,
-                    adapter_limits.max_compute_workgroup_size_z,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Compute Invocations Per Workgroup",
-                    &mut config_limits.max_compute_invocations_per_workgroup,
-                    adapter_limits.max_compute_invocations_per_workgroup,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Compute Workgroup Storage Size",
-                    &mut config_limits.max_compute_workgroup_storage_size,
-                    adapter_limits.max_compute_workgroup_storage_size,
-                    1,
-                );
-
-                Self::render_limit_u32(
-                    ui,
-                    "Max Compute Workgroups Per Dimension",
-                    &mut config_limits.max_compute_workgroups_per_dimension,
-                    adapter_limits.max_compute_workgroups_per_dimension,
-                    1,
-                );
+                for field in LIMIT_FIELDS {
+                    Self::render_limit(ui, field, config_limits, adapter_limits);
+                }
             });
 
         ui.add_space(10.0);
@@ -347,27 +513,22 @@ impl DeviceConfigPanel {
         });
     }
 
-    fn render_limit_u32(ui: &mut egui::Ui, label: &str, value: &mut u32, max: u32, min: u32) {
-        ui.label(label);
-        let mut temp_value = *value;
+    fn render_limit(
+        ui: &mut egui::Ui,
+        field: &LimitField,
+        limits: &mut Limits,
+        adapter_limits: &Limits,
+    ) {
+        let max = field.get(adapter_limits);
+        ui.label(field.label).on_hover_text(field.name);
+        let mut value = field.get(limits);
         if ui
-            .add(egui::DragValue::new(&mut temp_value).range(min..=max))
+            .add(egui::DragValue::new(&mut value).range(1..=max.max(1)))
             .changed()
         {
-            *value = temp_value;
-        }
-        ui.end_row();
-    }
-
-    fn render_limit_u64(ui: &mut egui::Ui, label: &str, value: &mut u64, max: u64, min: u64) {
-        ui.label(label);
-        let mut temp_value = *value;
-        if ui
-            .add(egui::DragValue::new(&mut temp_value).range(min..=max))
-            .changed()
-        {
-            *value = temp_value;
+            field.set(limits, value);
         }
+        ui.weak(format!("max {}", max));
         ui.end_row();
     }
 }
@@ -375,6 +536,7 @@ impl DeviceConfigPanel {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_device_config_default() {
@@ -414,4 +576,132 @@ mod tests {
         assert!(config.has_feature(Features::TIMESTAMP_QUERY));
         assert!(!config.has_feature(Features::SHADER_F16));
     }
+
+    #[test]
+    fn test_selectable_features_exclude_experimental() {
+        let selectable = DeviceConfig::selectable_features();
+        assert!(selectable.contains(Features::TIMESTAMP_QUERY));
+        assert!(!selectable.intersects(Features::all_experimental_mask()));
+    }
+
+    #[test]
+    fn test_descriptor_uses_config() {
+        let mut config = DeviceConfig::new();
+        config.features = Features::DEPTH_CLIP_CONTROL;
+        config.limits.max_bind_groups = 2;
+
+        let descriptor = config.descriptor();
+        assert_eq!(descriptor.required_features, Features::DEPTH_CLIP_CONTROL);
+        assert_eq!(descriptor.required_limits.max_bind_groups, 2);
+        assert_eq!(descriptor.label, Some(DEVICE_LABEL));
+    }
+
+    #[test]
+    fn test_unsupported_reports_features_and_limits() {
+        let mut config = DeviceConfig::new();
+        config.features = Features::TIMESTAMP_QUERY | Features::SHADER_F16;
+        config.limits.max_texture_dimension_2d = 16384;
+
+        let adapter_limits = Limits {
+            max_texture_dimension_2d: 8192,
+            ..Limits::default()
+        };
+        let problems = config.unsupported(Features::TIMESTAMP_QUERY, &adapter_limits);
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("SHADER_F16"));
+        assert!(problems[1].contains("max_texture_dimension_2d"));
+
+        assert!(config
+            .unsupported(
+                config.features,
+                &Limits {
+                    max_texture_dimension_2d: 16384,
+                    ..Limits::default()
+                }
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn test_restricted_to_adapter() {
+        let mut config = DeviceConfig::new();
+        config.features = Features::TIMESTAMP_QUERY | Features::SHADER_F16;
+        config.limits.max_texture_dimension_2d = 16384;
+
+        let adapter_limits = Limits {
+            max_texture_dimension_2d: 8192,
+            ..Limits::default()
+        };
+        let restricted = config.restricted_to(Features::SHADER_F16, &adapter_limits);
+        assert_eq!(restricted.features, Features::SHADER_F16);
+        assert_eq!(restricted.limits.max_texture_dimension_2d, 8192);
+        assert!(restricted
+            .unsupported(Features::SHADER_F16, &adapter_limits)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut config = DeviceConfig::new();
+        config.features = Features::DEPTH_CLIP_CONTROL | Features::TIMESTAMP_QUERY;
+        config.limits.max_bind_groups = 8;
+        config.limits.max_buffer_size = 1 << 30;
+
+        let state = config.to_state();
+        assert_eq!(state.features.len(), 2);
+        // Only limits that differ from the defaults are stored
+        assert_eq!(state.limits.len(), 2);
+        assert_eq!(state.limits.get("max_bind_groups"), Some(&8));
+
+        assert_eq!(DeviceConfig::from_state(&state), config);
+    }
+
+    #[test]
+    fn test_from_state_skips_unknown_names() {
+        let state = DeviceConfigState {
+            features: vec!["SHADER_F16".to_string(), "NOT_A_FEATURE".to_string()],
+            limits: BTreeMap::from([
+                ("max_vertex_buffers".to_string(), 12),
+                ("not_a_limit".to_string(), 1),
+            ]),
+        };
+        let config = DeviceConfig::from_state(&state);
+        assert_eq!(config.features, Features::SHADER_F16);
+        assert_eq!(config.limits.max_vertex_buffers, 12);
+    }
+
+    #[test]
+    fn test_limit_fields_access_their_limit() {
+        let mut limits = Limits::default();
+        for (i, field) in LIMIT_FIELDS.iter().enumerate() {
+            field.set(&mut limits, i as u64 + 1);
+        }
+        for (i, field) in LIMIT_FIELDS.iter().enumerate() {
+            assert_eq!(field.get(&limits), i as u64 + 1, "{}", field.name);
+        }
+    }
+
+    #[test]
+    fn test_panel_apply_request() {
+        let mut panel = DeviceConfigPanel::with_capabilities(Features::all(), Limits::default());
+        assert!(!panel.has_changes());
+        assert!(panel.take_apply_request().is_none());
+
+        let mut config = DeviceConfig::new();
+        config.features = Features::TIMESTAMP_QUERY;
+        panel.set_config(config.clone());
+        assert!(panel.has_changes());
+
+        panel.apply_requested = true;
+        assert_eq!(panel.take_apply_request(), Some(config.clone()));
+        assert!(panel.take_apply_request().is_none());
+
+        // A failed apply keeps the old device configuration
+        panel.finish_apply(Err("out of memory".to_string()));
+        assert!(panel.has_changes());
+
+        panel.finish_apply(Ok("Test Adapter".to_string()));
+        assert!(!panel.has_changes());
+        assert_eq!(panel.active_config(), &config);
+    }
 }
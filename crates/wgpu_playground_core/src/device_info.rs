@@ -1,4 +1,6 @@
+use crate::capability_snapshot::{CapabilitySnapshot, ProjectDescriptor, VirtualDevice};
 use crate::implementation::WebGPUImplementation;
+use crate::limits_explorer::LimitsExplorer;
 
 pub struct DeviceInfo {
     adapter_name: String,
@@ -7,6 +9,15 @@ pub struct DeviceInfo {
     device_limits: String,
     device_features: String,
     implementation: WebGPUImplementation,
+    snapshot: CapabilitySnapshot,
+    device_limits_raw: wgpu::Limits,
+    device_features_raw: wgpu::Features,
+    adapter_limits_raw: wgpu::Limits,
+    limits_explorer: LimitsExplorer,
+    snapshot_path: String,
+    snapshot_message: Option<String>,
+    virtual_device: Option<VirtualDevice>,
+    project_descriptors: Vec<ProjectDescriptor>,
 }
 
 impl DeviceInfo {
@@ -90,10 +101,43 @@ impl DeviceInfo {
             device_limits,
             device_features,
             implementation: WebGPUImplementation::current(),
+            snapshot: CapabilitySnapshot::from_adapter(adapter),
+            device_limits_raw: limits,
+            device_features_raw: features,
+            adapter_limits_raw: adapter.limits(),
+            limits_explorer: LimitsExplorer::new(),
+            snapshot_path: "device_capabilities.json".to_string(),
+            snapshot_message: None,
+            virtual_device: None,
+            project_descriptors: Vec::new(),
         }
     }
 
-    pub fn ui(&self, ui: &mut egui::Ui) {
+    /// Get the capability snapshot of the active adapter
+    pub fn snapshot(&self) -> &CapabilitySnapshot {
+        &self.snapshot
+    }
+
+    /// Get the loaded virtual device, if any
+    pub fn virtual_device(&self) -> Option<&VirtualDevice> {
+        self.virtual_device.as_ref()
+    }
+
+    /// Set the project's descriptors to validate against the virtual device
+    pub fn set_project_descriptors(&mut self, descriptors: Vec<ProjectDescriptor>) {
+        self.project_descriptors = descriptors;
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.ui_with_probes(ui, None);
+    }
+
+    /// Render the device info, with limit probes when a device is given
+    pub fn ui_with_probes(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: Option<(&wgpu::Device, &wgpu::Queue)>,
+    ) {
         egui::ScrollArea::vertical().show(ui, |ui| {
             // WebGPU Implementation section
             ui.heading("🔧 WebGPU Implementation");
@@ -165,9 +209,127 @@ impl DeviceInfo {
             ui.label(&self.device_limits);
             ui.add_space(20.0);
 
+            self.limits_explorer
+                .ui(ui, device, &self.adapter_limits_raw);
+            ui.add_space(20.0);
+
             ui.heading("Device Features");
             ui.separator();
             ui.label(&self.device_features);
+            ui.add_space(20.0);
+
+            self.render_snapshot_ui(ui);
         });
     }
+
+    fn render_snapshot_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("📸 Capability Snapshot");
+        ui.separator();
+        ui.label(
+            "Export this adapter's features, limits and format capabilities as JSON, or load \
+            a snapshot from another machine to check whether the current device configuration \
+            would run there.",
+        );
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.label("File:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.snapshot_path)
+                    .desired_width(250.0)
+                    .hint_text("device_capabilities.json"),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("💾 Export Snapshot")
+                .on_hover_text("Save the active adapter's capabilities to the file")
+                .clicked()
+            {
+                let path = std::path::Path::new(&self.snapshot_path);
+                self.snapshot_message = Some(match self.snapshot.save_to_file(path) {
+                    Ok(_) => format!("✓ Snapshot saved to {}", self.snapshot_path),
+                    Err(e) => format!("✗ Failed to save snapshot: {}", e),
+                });
+            }
+
+            if ui
+                .button("📂 Load as Virtual Device")
+                .on_hover_text("Load a snapshot and validate the current device against it")
+                .clicked()
+            {
+                let path = std::path::Path::new(&self.snapshot_path);
+                match CapabilitySnapshot::load_from_file(path) {
+                    Ok(snapshot) => {
+                        self.snapshot_message =
+                            Some(format!("✓ Loaded snapshot of {}", snapshot.adapter_name));
+                        self.virtual_device = Some(VirtualDevice::new(snapshot));
+                    }
+                    Err(e) => {
+                        self.snapshot_message = Some(format!("✗ Failed to load snapshot: {}", e));
+                    }
+                }
+            }
+
+            if self.virtual_device.is_some() && ui.button("✖ Unload").clicked() {
+                self.virtual_device = None;
+                self.snapshot_message = None;
+            }
+        });
+
+        if let Some(msg) = &self.snapshot_message {
+            ui.label(msg);
+        }
+
+        if let Some(virtual_device) = &self.virtual_device {
+            let snapshot = virtual_device.snapshot();
+            ui.add_space(5.0);
+            ui.label(format!(
+                "Virtual device: {} ({}, captured {})",
+                snapshot.adapter_name, snapshot.backend, snapshot.captured_at
+            ));
+
+            let mut issues = virtual_device.check_features(self.device_features_raw);
+            issues.extend(virtual_device.check_limits(&self.device_limits_raw));
+
+            if issues.is_empty() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(100, 200, 100),
+                    "✓ The current device configuration fits within the snapshot",
+                );
+            } else {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 165, 0),
+                    format!("⚠ {} incompatibilities found:", issues.len()),
+                );
+                for issue in &issues {
+                    ui.label(format!("  • {}", issue));
+                }
+            }
+
+            ui.add_space(5.0);
+            ui.label("Project descriptors:");
+            if self.project_descriptors.is_empty() {
+                ui.label("  No buffer, texture or compute descriptors configured");
+            }
+            for descriptor in &self.project_descriptors {
+                let issues = virtual_device.validate_descriptor(descriptor);
+                if issues.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(100, 200, 100),
+                        format!("✓ {}", descriptor),
+                    );
+                } else {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 165, 0),
+                        format!("⚠ {}:", descriptor),
+                    );
+                    for issue in &issues {
+                        ui.label(format!("  • {}", issue));
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,322 @@
+//! Persistence of the driver's compiled pipelines across launches
+//!
+//! Turning WGSL into GPU machine code is the slow part of pipeline creation.
+//! On backends with a pipeline cache (Vulkan in wgpu today) the driver can
+//! hand that work back as opaque data: [`DevicePipelineCache::open`] creates
+//! a `wgpu::PipelineCache` from the data a previous launch saved, the
+//! playground passes it to every pipeline it creates through [`for_device`],
+//! and [`DevicePipelineCache::save`] writes the grown cache back on exit.
+//! Data is stored per adapter under the name
+//! `wgpu::util::pipeline_cache_key` gives it, in the platform's cache
+//! directory.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::asset_cache::CACHE_DIR_ENV;
+
+/// Subdirectory of the cache directory holding pipeline cache data
+pub const PIPELINE_CACHE_DIR: &str = "pipelines";
+
+/// Error of the device pipeline cache
+#[derive(Debug)]
+pub enum DevicePipelineCacheError {
+    /// The backend or device has no pipeline cache
+    Unsupported(String),
+    /// No cache is open
+    NotOpen,
+    /// Reading or writing the cache file failed
+    Io(io::Error),
+}
+
+impl fmt::Display for DevicePipelineCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DevicePipelineCacheError::Unsupported(reason) => {
+                write!(f, "Pipeline caching is not available: {}", reason)
+            }
+            DevicePipelineCacheError::NotOpen => write!(f, "No pipeline cache is open"),
+            DevicePipelineCacheError::Io(e) => write!(f, "Pipeline cache file error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DevicePipelineCacheError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DevicePipelineCacheError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DevicePipelineCacheError {
+    fn from(e: io::Error) -> Self {
+        DevicePipelineCacheError::Io(e)
+    }
+}
+
+/// Where the playground keeps caches in the platform's cache location
+fn platform_cache_dir() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    #[cfg(target_os = "windows")]
+    let base = var("LOCALAPPDATA").map(PathBuf::from);
+    #[cfg(target_os = "macos")]
+    let base = var("HOME").map(|home| PathBuf::from(home).join("Library").join("Caches"));
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let base = var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".cache")));
+    base.map(|dir| dir.join("wgpu_playground"))
+}
+
+/// Directory of the pipeline cache data
+///
+/// Below [`CACHE_DIR_ENV`] if it is set, like the asset cache, and
+/// otherwise below the platform's cache directory.
+pub fn default_dir() -> PathBuf {
+    let root = std::env::var_os(CACHE_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(platform_cache_dir)
+        .unwrap_or_else(|| PathBuf::from(crate::asset_cache::DEFAULT_CACHE_DIR));
+    root.join(PIPELINE_CACHE_DIR)
+}
+
+/// Features to request for a device on `adapter` so that its pipelines can
+/// be cached
+pub fn required_features(adapter: &wgpu::Adapter) -> wgpu::Features {
+    if wgpu::util::pipeline_cache_key(&adapter.get_info()).is_some() {
+        adapter.features() & wgpu::Features::PIPELINE_CACHE
+    } else {
+        wgpu::Features::empty()
+    }
+}
+
+/// The cache to create pipelines on `device` with, if one is open for it
+///
+/// Meant for the `cache` field of pipeline descriptors.
+pub fn for_device(device: &wgpu::Device) -> Option<wgpu::PipelineCache> {
+    DevicePipelineCache::global().for_device(device)
+}
+
+/// Size and location of the open cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineCacheInfo {
+    /// File the data is saved to
+    pub path: PathBuf,
+    /// Bytes loaded from the file when the cache was opened
+    pub loaded_bytes: usize,
+    /// Bytes the cache would save now
+    pub data_bytes: usize,
+}
+
+/// The cache of the current device
+struct OpenCache {
+    device: wgpu::Device,
+    cache: wgpu::PipelineCache,
+    path: PathBuf,
+    loaded_bytes: usize,
+}
+
+impl OpenCache {
+    fn create(device: &wgpu::Device, data: Option<&[u8]>) -> wgpu::PipelineCache {
+        ApiCoverageTracker::global().record(ApiCategory::Device, "create_pipeline_cache");
+        // SAFETY: the data was returned by `get_data` for an adapter with the
+        // same `pipeline_cache_key`, which names the file it is read from.
+        // With `fallback` set, data the driver rejects yields an empty cache.
+        unsafe {
+            device.create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                label: Some("Playground Pipeline Cache"),
+                data,
+                fallback: true,
+            })
+        }
+    }
+
+    /// Write the data atomically, through a temporary file
+    fn save(&self) -> Result<usize, DevicePipelineCacheError> {
+        let data = self.cache.get_data().unwrap_or_default();
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let temp = self.path.with_extension("tmp");
+        std::fs::write(&temp, &data)?;
+        std::fs::rename(&temp, &self.path)?;
+        Ok(data.len())
+    }
+}
+
+/// The driver pipeline cache of the playground's device
+pub struct DevicePipelineCache {
+    open: Mutex<Option<OpenCache>>,
+}
+
+impl Default for DevicePipelineCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DevicePipelineCache {
+    /// Create a holder with no cache open
+    pub fn new() -> Self {
+        Self {
+            open: Mutex::new(None),
+        }
+    }
+
+    /// The cache used by the whole playground
+    pub fn global() -> &'static DevicePipelineCache {
+        static GLOBAL: OnceLock<DevicePipelineCache> = OnceLock::new();
+        GLOBAL.get_or_init(DevicePipelineCache::new)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<OpenCache>> {
+        self.open.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Open the cache for `device` in [`default_dir`]
+    pub fn open(
+        &self,
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+    ) -> Result<PipelineCacheInfo, DevicePipelineCacheError> {
+        self.open_in(&default_dir(), adapter, device)
+    }
+
+    /// Open the cache for `device`, loading the data saved in `dir` for its
+    /// adapter
+    ///
+    /// A cache open for a previous device is saved and closed first. The
+    /// device needs the `PIPELINE_CACHE` feature, see [`required_features`].
+    pub fn open_in(
+        &self,
+        dir: &Path,
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+    ) -> Result<PipelineCacheInfo, DevicePipelineCacheError> {
+        let mut open = self.lock();
+        if let Some(previous) = open.take() {
+            if let Err(e) = previous.save() {
+                log::warn!("Failed to save the previous pipeline cache: {}", e);
+            }
+        }
+
+        let info = adapter.get_info();
+        let key = wgpu::util::pipeline_cache_key(&info).ok_or_else(|| {
+            DevicePipelineCacheError::Unsupported(format!(
+                "the {:?} backend has no pipeline cache",
+                info.backend
+            ))
+        })?;
+        if !device.features().contains(wgpu::Features::PIPELINE_CACHE) {
+            return Err(DevicePipelineCacheError::Unsupported(
+                "the device was created without the PIPELINE_CACHE feature".to_string(),
+            ));
+        }
+
+        let path = dir.join(key);
+        let data = match std::fs::read(&path) {
+            Ok(data) => Some(data),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => {
+                log::warn!("Ignoring unreadable pipeline cache {:?}: {}", path, e);
+                None
+            }
+        };
+        let loaded_bytes = data.as_ref().map_or(0, Vec::len);
+        let cache = OpenCache::create(device, data.as_deref());
+        log::info!(
+            "Opened pipeline cache {:?} with {} bytes of saved data",
+            path,
+            loaded_bytes
+        );
+
+        let opened = OpenCache {
+            device: device.clone(),
+            cache,
+            path,
+            loaded_bytes,
+        };
+        let info = PipelineCacheInfo {
+            path: opened.path.clone(),
+            loaded_bytes,
+            data_bytes: loaded_bytes,
+        };
+        *open = Some(opened);
+        Ok(info)
+    }
+
+    /// The cache to create pipelines on `device` with
+    ///
+    /// `None` if no cache is open or it belongs to another device, such as
+    /// one created by a test or benchmark.
+    pub fn for_device(&self, device: &wgpu::Device) -> Option<wgpu::PipelineCache> {
+        self.lock()
+            .as_ref()
+            .filter(|open| open.device == *device)
+            .map(|open| open.cache.clone())
+    }
+
+    /// Size and location of the open cache
+    pub fn info(&self) -> Option<PipelineCacheInfo> {
+        self.lock().as_ref().map(|open| PipelineCacheInfo {
+            path: open.path.clone(),
+            loaded_bytes: open.loaded_bytes,
+            data_bytes: open.cache.get_data().map_or(0, |data| data.len()),
+        })
+    }
+
+    /// Save the data of the open cache, returning its size in bytes
+    pub fn save(&self) -> Result<usize, DevicePipelineCacheError> {
+        let open = self.lock();
+        let open = open.as_ref().ok_or(DevicePipelineCacheError::NotOpen)?;
+        let bytes = open.save()?;
+        log::info!("Saved {} bytes of pipeline cache to {:?}", bytes, open.path);
+        Ok(bytes)
+    }
+
+    /// Delete the saved data and continue with an empty cache
+    ///
+    /// Pipelines created from now on are compiled by the driver again.
+    pub fn clear(&self) -> Result<(), DevicePipelineCacheError> {
+        let mut open = self.lock();
+        let open = open.as_mut().ok_or(DevicePipelineCacheError::NotOpen)?;
+        match std::fs::remove_file(&open.path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        open.cache = OpenCache::create(&open.device, None);
+        open.loaded_bytes = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_dir_ends_in_pipelines() {
+        assert!(default_dir().ends_with(PIPELINE_CACHE_DIR));
+    }
+
+    #[test]
+    fn test_nothing_open() {
+        let cache = DevicePipelineCache::new();
+        assert!(cache.info().is_none());
+        assert!(matches!(
+            cache.save(),
+            Err(DevicePipelineCacheError::NotOpen)
+        ));
+        assert!(matches!(
+            cache.clear(),
+            Err(DevicePipelineCacheError::NotOpen)
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+//! Device loss detection and recovery
+//!
+//! A GPU device can be lost at any time: the driver crashes or is updated,
+//! the GPU is reset, or the device is destroyed on purpose. Everything
+//! created on a lost device is unusable. The application watches for loss
+//! with a [`DeviceLossMonitor`], requests a replacement device, and hands it
+//! to every component through [`ResourceRecreate`] so previews and cached
+//! pipelines are rebuilt without a restart.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::DeviceLostReason;
+
+/// Message reported for a loss triggered by [`DeviceLossMonitor::simulate`]
+pub const SIMULATED_LOSS_MESSAGE: &str = "Simulated device loss";
+
+/// Something that owns GPU resources and can rebuild them on a new device
+pub trait ResourceRecreate {
+    /// Drop everything created on the lost device and rebuild what is shown
+    ///
+    /// Called once the replacement device is ready. Resources that are
+    /// created on demand only need to be dropped.
+    fn recreate_resources(&mut self, device: &wgpu::Device, queue: &wgpu::Queue);
+}
+
+/// A device loss reported by the device lost callback
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceLoss {
+    pub reason: DeviceLostReason,
+    pub message: String,
+    /// Whether the loss was triggered by [`DeviceLossMonitor::simulate`]
+    pub simulated: bool,
+}
+
+/// Records when the watched device is lost
+///
+/// Only the most recently watched device counts: losses of a device that
+/// has already been replaced, including the one reported when it is
+/// dropped, are ignored. Cloning is cheap and clones share the state.
+#[derive(Clone, Default)]
+pub struct DeviceLossMonitor {
+    lost: Arc<Mutex<Option<DeviceLoss>>>,
+    /// Incremented for every watched device
+    generation: Arc<AtomicU64>,
+    simulating: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for DeviceLossMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceLossMonitor")
+            .field("lost", &*self.lock())
+            .field("generation", &self.generation.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+impl DeviceLossMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<DeviceLoss>> {
+        self.lost.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Install the device lost callback on `device`
+    ///
+    /// Replaces the logging callback set by
+    /// [`setup_device_error_handling`](crate::error::setup_device_error_handling),
+    /// so call this after it. A pending loss of the previous device is
+    /// discarded.
+    pub fn watch(&self, device: &wgpu::Device) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.simulating.store(false, Ordering::SeqCst);
+        *self.lock() = None;
+
+        let monitor = self.clone();
+        device.set_device_lost_callback(move |reason, message| {
+            let reason = DeviceLostReason::from(reason);
+            if monitor.generation.load(Ordering::SeqCst) != generation {
+                log::debug!("Ignoring loss of a replaced device: {}", message);
+                return;
+            }
+            log::error!("Device lost! Reason: {}, Message: {}", reason, message);
+            monitor.record(DeviceLoss {
+                reason,
+                message,
+                simulated: monitor.simulating.load(Ordering::SeqCst),
+            });
+        });
+    }
+
+    fn record(&self, loss: DeviceLoss) {
+        let mut lost = self.lock();
+        if lost.is_none() {
+            *lost = Some(loss);
+        }
+    }
+
+    /// Destroy the watched device to exercise the recovery path
+    pub fn simulate(&self, device: &wgpu::Device) {
+        log::warn!("Simulating device loss");
+        self.simulating.store(true, Ordering::SeqCst);
+        device.destroy();
+        // Backends may report the loss later or not at all for a destroyed
+        // device, so record it right away
+        self.record(DeviceLoss {
+            reason: DeviceLostReason::Destroyed,
+            message: SIMULATED_LOSS_MESSAGE.to_string(),
+            simulated: true,
+        });
+    }
+
+    /// Whether the watched device has been lost
+    pub fn is_lost(&self) -> bool {
+        self.lock().is_some()
+    }
+
+    /// Take the pending loss, if any
+    ///
+    /// Returns `Some` once per loss; the caller is expected to replace the
+    /// device and [`watch`](Self::watch) the new one.
+    pub fn take(&self) -> Option<DeviceLoss> {
+        self.lock().take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_loss_is_kept() {
+        let monitor = DeviceLossMonitor::new();
+        assert!(!monitor.is_lost());
+        monitor.record(DeviceLoss {
+            reason: DeviceLostReason::Unknown,
+            message: "GPU reset".to_string(),
+            simulated: false,
+        });
+        monitor.record(DeviceLoss {
+            reason: DeviceLostReason::Destroyed,
+            message: "dropped".to_string(),
+            simulated: false,
+        });
+        assert!(monitor.clone().is_lost());
+
+        let loss = monitor.take().unwrap();
+        assert_eq!(loss.reason, DeviceLostReason::Unknown);
+        assert_eq!(loss.message, "GPU reset");
+        assert!(monitor.take().is_none());
+    }
+}
@@ -351,7 +351,7 @@ impl DrawCommandPanel {
                 .spacing([10.0, 8.0])
                 .show(ui, |ui| {
                     ui.label("Vertex Count:").webgpu_tooltip(
-                        crate::tooltip::draw::VERTEX_COUNT.description,
+                        &crate::tooltip::draw::VERTEX_COUNT.description(),
                         crate::tooltip::draw::VERTEX_COUNT.spec_anchor,
                     );
                     ui.add(
@@ -361,7 +361,7 @@ impl DrawCommandPanel {
                     ui.end_row();
 
                     ui.label("First Vertex:").webgpu_tooltip(
-                        crate::tooltip::draw::FIRST_VERTEX.description,
+                        &crate::tooltip::draw::FIRST_VERTEX.description(),
                         crate::tooltip::draw::FIRST_VERTEX.spec_anchor,
                     );
                     ui.add(
@@ -371,7 +371,7 @@ impl DrawCommandPanel {
                     ui.end_row();
 
                     ui.label("Instance Count:").webgpu_tooltip(
-                        crate::tooltip::draw::INSTANCE_COUNT.description,
+                        &crate::tooltip::draw::INSTANCE_COUNT.description(),
                         crate::tooltip::draw::INSTANCE_COUNT.spec_anchor,
                     );
                     ui.add(
@@ -381,7 +381,7 @@ impl DrawCommandPanel {
                     ui.end_row();
 
                     ui.label("First Instance:").webgpu_tooltip(
-                        crate::tooltip::draw::FIRST_INSTANCE.description,
+                        &crate::tooltip::draw::FIRST_INSTANCE.description(),
                         crate::tooltip::draw::FIRST_INSTANCE.spec_anchor,
                     );
                     ui.add(
@@ -425,7 +425,7 @@ impl DrawCommandPanel {
                     ui.end_row();
 
                     ui.label("Instance Count:").webgpu_tooltip(
-                        crate::tooltip::draw::INSTANCE_COUNT.description,
+                        &crate::tooltip::draw::INSTANCE_COUNT.description(),
                         crate::tooltip::draw::INSTANCE_COUNT.spec_anchor,
                     );
                     ui.add(
@@ -435,7 +435,7 @@ impl DrawCommandPanel {
                     ui.end_row();
 
                     ui.label("First Instance:").webgpu_tooltip(
-                        crate::tooltip::draw::FIRST_INSTANCE.description,
+                        &crate::tooltip::draw::FIRST_INSTANCE.description(),
                         crate::tooltip::draw::FIRST_INSTANCE.spec_anchor,
                     );
                     ui.add(
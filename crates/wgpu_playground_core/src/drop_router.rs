@@ -0,0 +1,302 @@
+//! Routing files dropped on the window to the panel that opens them
+//!
+//! The panel is picked from the file's extension (or, on the web, its mime
+//! type): shaders go to the shader editor, images to the Textures tab, models
+//! to the model importer and `.wgplay` projects replace the workspace. While
+//! files are dragged over the window, [`overlay_ui`] shows every target with
+//! the one the file will land in highlighted.
+
+use std::path::Path;
+
+use crate::i18n::{tr, tr_args};
+
+/// Extension of saved playground projects
+pub const PROJECT_EXTENSION: &str = "wgplay";
+
+/// Where a dropped file is opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropTarget {
+    /// WGSL source, opened in the shader editor of the Rendering tab
+    ShaderEditor,
+    /// Image, loaded in the Textures tab
+    Texture,
+    /// Mesh, imported in the Models tab
+    Model,
+    /// Saved playground state, replacing the active workspace's
+    Project,
+}
+
+impl DropTarget {
+    pub const ALL: [DropTarget; 4] = [
+        DropTarget::ShaderEditor,
+        DropTarget::Texture,
+        DropTarget::Model,
+        DropTarget::Project,
+    ];
+
+    /// Lowercase extensions routed to the target
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            DropTarget::ShaderEditor => &["wgsl"],
+            DropTarget::Texture => &["png", "jpg", "jpeg", "ktx2", "svg"],
+            DropTarget::Model => &["gltf", "glb", "obj", "ply", "stl"],
+            DropTarget::Project => &[PROJECT_EXTENSION],
+        }
+    }
+
+    /// Target of files with extension `extension`, in any case
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        let extension = extension.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|target| target.extensions().contains(&extension.as_str()))
+    }
+
+    /// Target of the file at `path`
+    pub fn for_path(path: &Path) -> Option<Self> {
+        Self::for_extension(path.extension()?.to_str()?)
+    }
+
+    /// Target of a file of mime type `mime`, for drops without a path
+    pub fn for_mime(mime: &str) -> Option<Self> {
+        match mime {
+            "image/png" | "image/jpeg" | "image/ktx2" | "image/svg+xml" => {
+                Some(DropTarget::Texture)
+            }
+            "model/gltf+json" | "model/gltf-binary" | "model/obj" | "model/stl" => {
+                Some(DropTarget::Model)
+            }
+            _ => None,
+        }
+    }
+
+    /// Target of a file being dragged over the window
+    pub fn for_hovered(file: &egui::HoveredFile) -> Option<Self> {
+        match &file.path {
+            Some(path) => Self::for_path(path),
+            None => Self::for_mime(&file.mime),
+        }
+    }
+
+    /// Target of a file dropped on the window
+    pub fn for_dropped(file: &egui::DroppedFile) -> Option<Self> {
+        match &file.path {
+            Some(path) => Self::for_path(path),
+            None => Self::for_path(Path::new(&file.name)).or_else(|| Self::for_mime(&file.mime)),
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            DropTarget::ShaderEditor => "📝",
+            DropTarget::Texture => "🖼",
+            DropTarget::Model => "🧊",
+            DropTarget::Project => "📂",
+        }
+    }
+
+    /// Display name in the current language
+    pub fn name(&self) -> String {
+        tr(match self {
+            DropTarget::ShaderEditor => "drop-target-shader",
+            DropTarget::Texture => "drop-target-texture",
+            DropTarget::Model => "drop-target-model",
+            DropTarget::Project => "drop-target-project",
+        })
+    }
+}
+
+/// Name of a dropped file to show the user
+pub fn dropped_file_name(file: &egui::DroppedFile) -> String {
+    match &file.path {
+        Some(path) => path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+        None => file.name.clone(),
+    }
+}
+
+/// Contents of a dropped file: the bytes the web backend passes along, or
+/// those of the file on disk
+pub fn read_dropped_file(file: &egui::DroppedFile) -> std::io::Result<Vec<u8>> {
+    if let Some(bytes) = &file.bytes {
+        return Ok(bytes.to_vec());
+    }
+    match &file.path {
+        Some(path) => std::fs::read(path),
+        None => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "the dropped file has neither a path nor contents",
+        )),
+    }
+}
+
+/// Dim the window while files are dragged over it and show where the first
+/// one will be opened
+pub fn overlay_ui(ctx: &egui::Context) {
+    let hovered = ctx.input(|i| i.raw.hovered_files.clone());
+    let Some(first) = hovered.first() else {
+        return;
+    };
+    let target = DropTarget::for_hovered(first);
+    let file = first.path.as_deref().and_then(Path::file_name).map_or_else(
+        || first.mime.clone(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("drop_overlay"),
+    ));
+    let screen = ctx.content_rect();
+    painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(170));
+
+    let accent = ctx.style().visuals.selection.bg_fill;
+    let (headline, headline_color) = match target {
+        Some(target) => (
+            tr_args(
+                "drop-overlay-target",
+                &[("file", &file), ("target", &target.name())],
+            ),
+            egui::Color32::WHITE,
+        ),
+        None => (
+            tr_args("drop-overlay-unsupported", &[("file", &file)]),
+            egui::Color32::from_rgb(220, 80, 80),
+        ),
+    };
+    let center = screen.center();
+    painter.text(
+        center - egui::vec2(0.0, 100.0),
+        egui::Align2::CENTER_CENTER,
+        headline,
+        egui::FontId::proportional(22.0),
+        headline_color,
+    );
+    if hovered.len() > 1 {
+        painter.text(
+            center - egui::vec2(0.0, 74.0),
+            egui::Align2::CENTER_CENTER,
+            tr_args("drop-overlay-more", &[("count", &(hovered.len() - 1))]),
+            egui::FontId::proportional(14.0),
+            egui::Color32::LIGHT_GRAY,
+        );
+    }
+
+    // One card per target in a row, the file's target highlighted
+    let gap = 16.0;
+    let count = DropTarget::ALL.len() as f32;
+    let card_width = ((screen.width() - gap * (count + 1.0)) / count).clamp(80.0, 170.0);
+    let card = egui::vec2(card_width, 110.0);
+    let row_width = card.x * count + gap * (count - 1.0);
+    let mut min = egui::pos2(center.x - row_width / 2.0, center.y - card.y / 2.0);
+    for candidate in DropTarget::ALL {
+        let rect = egui::Rect::from_min_size(min, card);
+        let active = target == Some(candidate);
+        let (fill, stroke, text) = if active {
+            (
+                accent.gamma_multiply(0.35),
+                egui::Stroke::new(3.0, accent),
+                egui::Color32::WHITE,
+            )
+        } else {
+            (
+                egui::Color32::from_black_alpha(120),
+                egui::Stroke::new(1.0, egui::Color32::GRAY),
+                egui::Color32::GRAY,
+            )
+        };
+        painter.rect(rect, 8.0, fill, stroke, egui::StrokeKind::Inside);
+        painter.text(
+            rect.center_top() + egui::vec2(0.0, 28.0),
+            egui::Align2::CENTER_CENTER,
+            candidate.icon(),
+            egui::FontId::proportional(28.0),
+            text,
+        );
+        painter.text(
+            rect.center() + egui::vec2(0.0, 12.0),
+            egui::Align2::CENTER_CENTER,
+            candidate.name(),
+            egui::FontId::proportional(15.0),
+            text,
+        );
+        let extensions: Vec<String> = candidate
+            .extensions()
+            .iter()
+            .map(|extension| format!(".{}", extension))
+            .collect();
+        painter.text(
+            rect.center_bottom() - egui::vec2(0.0, 18.0),
+            egui::Align2::CENTER_CENTER,
+            extensions.join(" "),
+            egui::FontId::proportional(12.0),
+            egui::Color32::GRAY,
+        );
+        min.x += card.x + gap;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_by_extension() {
+        assert_eq!(
+            DropTarget::for_path(Path::new("shaders/blur.wgsl")),
+            Some(DropTarget::ShaderEditor)
+        );
+        assert_eq!(
+            DropTarget::for_path(Path::new("Photo.JPG")),
+            Some(DropTarget::Texture)
+        );
+        assert_eq!(
+            DropTarget::for_path(Path::new("env.ktx2")),
+            Some(DropTarget::Texture)
+        );
+        assert_eq!(
+            DropTarget::for_path(Path::new("icon.svg")),
+            Some(DropTarget::Texture)
+        );
+        assert_eq!(
+            DropTarget::for_path(Path::new("scene.gltf")),
+            Some(DropTarget::Model)
+        );
+        assert_eq!(
+            DropTarget::for_path(Path::new("bunny.obj")),
+            Some(DropTarget::Model)
+        );
+        assert_eq!(
+            DropTarget::for_path(Path::new("scan.PLY")),
+            Some(DropTarget::Model)
+        );
+        assert_eq!(
+            DropTarget::for_path(Path::new("demo.wgplay")),
+            Some(DropTarget::Project)
+        );
+        assert_eq!(DropTarget::for_path(Path::new("notes.txt")), None);
+        assert_eq!(DropTarget::for_path(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn test_web_drops_use_name_then_mime() {
+        let named = egui::DroppedFile {
+            name: "lights.wgsl".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            DropTarget::for_dropped(&named),
+            Some(DropTarget::ShaderEditor)
+        );
+        let unnamed = egui::DroppedFile {
+            mime: "image/png".to_string(),
+            bytes: Some(vec![1, 2, 3].into()),
+            ..Default::default()
+        };
+        assert_eq!(DropTarget::for_dropped(&unnamed), Some(DropTarget::Texture));
+        assert_eq!(read_dropped_file(&unnamed).unwrap(), vec![1, 2, 3]);
+        assert!(read_dropped_file(&egui::DroppedFile::default()).is_err());
+    }
+}
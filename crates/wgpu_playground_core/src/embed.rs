@@ -0,0 +1,193 @@
+//! Stable API for embedding playground panels in other egui applications
+//!
+//! The panels in this crate only need an [`egui::Ui`] and, optionally, the
+//! host's wgpu device and queue. This module re-exports the panels that work
+//! well as standalone debugging widgets and gives them a common
+//! [`EmbeddedPanel`] interface, so a host application never has to touch the
+//! playground GUI itself.
+//!
+//! ```no_run
+//! use wgpu_playground_core::embed::{self, EmbedContext, ShaderEditor};
+//!
+//! fn debug_windows(
+//!     ctx: &egui::Context,
+//!     editor: &mut ShaderEditor,
+//!     open: &mut bool,
+//!     device: &wgpu::Device,
+//!     queue: &wgpu::Queue,
+//! ) {
+//!     // Called inside the host's egui frame
+//!     let mut embed_ctx = EmbedContext::new(device, queue);
+//!     embed::show_window(ctx, editor, open, &mut embed_ctx);
+//! }
+//! ```
+//!
+//! See `examples/embed.rs` for a complete winit host.
+
+pub use crate::buffer_inspector::{BufferInspector, DataFormat};
+pub use crate::console::{ConsoleMessage, ConsolePanel, Severity};
+pub use crate::shader_editor::ShaderEditor;
+pub use crate::shader_translation_panel::ShaderTranslationPanel;
+pub use crate::texture_inspector::{TextureData, TextureInspector};
+pub use crate::texture_panel::TexturePanel;
+
+/// GPU handles a host shares with embedded panels
+///
+/// All handles are optional; panels degrade to configuration-only UI when
+/// they are missing.
+#[derive(Default)]
+pub struct EmbedContext<'a> {
+    pub device: Option<&'a wgpu::Device>,
+    pub queue: Option<&'a wgpu::Queue>,
+    /// Renderer used to register texture previews with egui
+    #[cfg(not(target_arch = "wasm32"))]
+    pub renderer: Option<&'a mut egui_wgpu::Renderer>,
+}
+
+impl<'a> EmbedContext<'a> {
+    /// Context without GPU access
+    pub fn headless() -> Self {
+        Self::default()
+    }
+
+    /// Context sharing the host's device and queue
+    pub fn new(device: &'a wgpu::Device, queue: &'a wgpu::Queue) -> Self {
+        Self {
+            device: Some(device),
+            queue: Some(queue),
+            ..Self::default()
+        }
+    }
+
+    /// Also share the host's egui renderer, enabling texture previews
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_renderer(mut self, renderer: &'a mut egui_wgpu::Renderer) -> Self {
+        self.renderer = Some(renderer);
+        self
+    }
+}
+
+/// A playground panel that can be drawn inside any egui layout
+pub trait EmbeddedPanel {
+    /// Title suitable for a window or tab
+    fn title(&self) -> &'static str;
+
+    /// Draw the panel into `ui`
+    fn show(&mut self, ui: &mut egui::Ui, ctx: &mut EmbedContext<'_>);
+}
+
+/// Show a panel in its own resizable egui window
+pub fn show_window(
+    egui_ctx: &egui::Context,
+    panel: &mut impl EmbeddedPanel,
+    open: &mut bool,
+    ctx: &mut EmbedContext<'_>,
+) {
+    egui::Window::new(panel.title())
+        .open(open)
+        .resizable(true)
+        .default_size([520.0, 480.0])
+        .show(egui_ctx, |ui| panel.show(ui, ctx));
+}
+
+impl EmbeddedPanel for ShaderEditor {
+    fn title(&self) -> &'static str {
+        "Shader Editor"
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show(&mut self, ui: &mut egui::Ui, ctx: &mut EmbedContext<'_>) {
+        match (ctx.device, ctx.queue, ctx.renderer.as_deref_mut()) {
+            (Some(device), Some(queue), Some(renderer)) => {
+                self.ui_with_preview(ui, device, queue, renderer)
+            }
+            (device, _, _) => self.ui(ui, device),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn show(&mut self, ui: &mut egui::Ui, ctx: &mut EmbedContext<'_>) {
+        self.ui(ui, ctx.device);
+    }
+}
+
+impl EmbeddedPanel for TexturePanel {
+    fn title(&self) -> &'static str {
+        "Texture Configuration"
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show(&mut self, ui: &mut egui::Ui, ctx: &mut EmbedContext<'_>) {
+        self.ui_with_preview(ui, ctx.device, ctx.queue, ctx.renderer.as_deref_mut());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn show(&mut self, ui: &mut egui::Ui, ctx: &mut EmbedContext<'_>) {
+        self.ui_with_preview(ui, ctx.device, ctx.queue);
+    }
+}
+
+impl EmbeddedPanel for TextureInspector {
+    fn title(&self) -> &'static str {
+        "Texture Inspector"
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui, _ctx: &mut EmbedContext<'_>) {
+        self.ui(ui);
+    }
+}
+
+impl EmbeddedPanel for BufferInspector {
+    fn title(&self) -> &'static str {
+        "Buffer Inspector"
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show(&mut self, ui: &mut egui::Ui, ctx: &mut EmbedContext<'_>) {
+        self.ui_with_image(ui, ctx.device, ctx.queue, ctx.renderer.as_deref_mut());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn show(&mut self, ui: &mut egui::Ui, ctx: &mut EmbedContext<'_>) {
+        self.ui_with_image(ui, ctx.device, ctx.queue);
+    }
+}
+
+impl EmbeddedPanel for ConsolePanel {
+    fn title(&self) -> &'static str {
+        "Console"
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui, _ctx: &mut EmbedContext<'_>) {
+        self.ui(ui);
+    }
+}
+
+impl EmbeddedPanel for ShaderTranslationPanel {
+    fn title(&self) -> &'static str {
+        "Shader Translation"
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui, _ctx: &mut EmbedContext<'_>) {
+        self.ui(ui);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_titles_are_distinct() {
+        let titles = [
+            ShaderEditor::new().title(),
+            TexturePanel::new().title(),
+            TextureInspector::new().title(),
+            BufferInspector::new().title(),
+            ConsolePanel::new().title(),
+            ShaderTranslationPanel::new().title(),
+        ];
+        let unique: std::collections::HashSet<_> = titles.iter().collect();
+        assert_eq!(unique.len(), titles.len());
+    }
+}
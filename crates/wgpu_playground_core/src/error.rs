@@ -2,6 +2,7 @@
 ///
 /// This module provides error types, error scopes, and utilities for handling
 /// validation errors, out-of-memory errors, and internal errors.
+use crate::i18n::tr_args;
 use std::fmt;
 use std::sync::{Arc, Mutex};
 
@@ -88,13 +89,13 @@ impl From<wgpu::Error> for Error {
     fn from(err: wgpu::Error) -> Self {
         match err {
             wgpu::Error::OutOfMemory { source } => {
-                Error::out_of_memory(format!("GPU out of memory: {}", source))
+                Error::out_of_memory(tr_args("error-gpu-out-of-memory", &[("source", &source)]))
             }
             wgpu::Error::Validation { source, .. } => {
-                Error::validation(format!("Validation error: {}", source))
+                Error::validation(tr_args("error-gpu-validation", &[("source", &source)]))
             }
             wgpu::Error::Internal { source, .. } => {
-                Error::internal(format!("Internal error: {}", source))
+                Error::internal(tr_args("error-gpu-internal", &[("source", &source)]))
             }
         }
     }
@@ -280,6 +281,16 @@ pub type DeviceLostCallback = Box<dyn Fn(DeviceLostReason, String) + Send + 'sta
 /// - Device lost callback
 /// - Default logging for all errors
 ///
+/// Panels capture the errors of their own operations with
+/// [`OperationErrors`](crate::operation_errors::OperationErrors) and show
+/// them inline, so the uncaptured error callback only sees errors from work
+/// outside a panel operation, such as per-frame rendering. Applications that
+/// handle device loss themselves only need [`log_uncaptured_errors`].
+///
+/// The device lost callback only logs. To recover from device loss, install
+/// a [`DeviceLossMonitor`](crate::device_recovery::DeviceLossMonitor)
+/// afterwards.
+///
 /// # Arguments
 /// * `device` - The GPU device to configure
 ///
@@ -292,11 +303,7 @@ pub type DeviceLostCallback = Box<dyn Fn(DeviceLostReason, String) + Send + 'sta
 /// # }
 /// ```
 pub fn setup_device_error_handling(device: &wgpu::Device) {
-    // Set up uncaptured error callback
-    device.on_uncaptured_error(Arc::new(|error| {
-        let err = Error::from(error);
-        log::error!("Uncaptured GPU error: {}", err);
-    }));
+    log_uncaptured_errors(device);
 
     // Set up device lost callback
     device.set_device_lost_callback(|reason, message| {
@@ -307,6 +314,19 @@ pub fn setup_device_error_handling(device: &wgpu::Device) {
     log::info!("Device error handling configured");
 }
 
+/// Log errors that no error scope captured instead of panicking
+///
+/// This is the fallback for work outside a panel operation, such as
+/// per-frame rendering; errors of panel operations are captured by their
+/// [`OperationErrors`](crate::operation_errors::OperationErrors) scopes and
+/// never reach it.
+pub fn log_uncaptured_errors(device: &wgpu::Device) {
+    device.on_uncaptured_error(Arc::new(|error| {
+        let err = Error::from(error);
+        log::error!("GPU error outside a panel operation: {}", err);
+    }));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
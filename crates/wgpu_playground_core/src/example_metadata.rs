@@ -15,7 +15,7 @@ pub fn get_example_api_tags(example_id: &str) -> Vec<ApiCategory> {
             ApiCategory::CommandEncoder,
             ApiCategory::Queue,
         ],
-        "cube" => vec![
+        "cube" | "instanced_cubes" => vec![
             ApiCategory::Buffer,
             ApiCategory::Shader,
             ApiCategory::RenderPipeline,
@@ -24,7 +24,142 @@ pub fn get_example_api_tags(example_id: &str) -> Vec<ApiCategory> {
             ApiCategory::CommandEncoder,
             ApiCategory::Queue,
         ],
-        "texture_mapping" => vec![
+        "texture_mapping" | "tile_streaming" | "bindless_materials" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Texture,
+            ApiCategory::Sampler,
+            ApiCategory::Shader,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "lod_meshes" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "boids" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "fluid_simulation" => vec![
+            ApiCategory::Texture,
+            ApiCategory::Sampler,
+            ApiCategory::Buffer,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "compute_grass" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Texture,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "terrain" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Texture,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "ray_tracing" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Texture,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "hardware_ray_tracing" => vec![
+            ApiCategory::Device,
+            ApiCategory::Buffer,
+            ApiCategory::Shader,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "mesh_shading" => vec![
+            ApiCategory::Device,
+            ApiCategory::Buffer,
+            ApiCategory::Shader,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "text_rendering" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Texture,
+            ApiCategory::Sampler,
+            ApiCategory::Shader,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "water" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Texture,
+            ApiCategory::Sampler,
+            ApiCategory::Shader,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "volumetric_fog" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Texture,
+            ApiCategory::Sampler,
+            ApiCategory::Shader,
+            ApiCategory::RenderPipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::RenderPass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "shadow_bias" => vec![
             ApiCategory::Buffer,
             ApiCategory::Texture,
             ApiCategory::Sampler,
@@ -44,6 +179,25 @@ pub fn get_example_api_tags(example_id: &str) -> Vec<ApiCategory> {
             ApiCategory::CommandEncoder,
             ApiCategory::Queue,
         ],
+        "gaussian_blur" | "sobel_edges" | "histogram_equalization" => vec![
+            ApiCategory::Texture,
+            ApiCategory::Buffer,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
+        "parallel_reduction" | "prefix_scan" | "bitonic_sort" => vec![
+            ApiCategory::Buffer,
+            ApiCategory::Shader,
+            ApiCategory::ComputePipeline,
+            ApiCategory::BindGroup,
+            ApiCategory::ComputePass,
+            ApiCategory::CommandEncoder,
+            ApiCategory::Queue,
+        ],
         _ => vec![],
     }
 }
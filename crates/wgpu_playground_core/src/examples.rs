@@ -10,6 +10,10 @@ pub enum ExampleCategory {
     Rendering,
     /// GPU compute shader examples
     Compute,
+    /// Compute-shader filters applied to a loaded texture
+    ImageProcessing,
+    /// Parallel algorithms over large buffers, timed against the CPU
+    Algorithms,
 }
 
 impl fmt::Display for ExampleCategory {
@@ -17,10 +21,78 @@ impl fmt::Display for ExampleCategory {
         match self {
             ExampleCategory::Rendering => write!(f, "Rendering"),
             ExampleCategory::Compute => write!(f, "Compute"),
+            ExampleCategory::ImageProcessing => write!(f, "Image Processing"),
+            ExampleCategory::Algorithms => write!(f, "Algorithms"),
         }
     }
 }
 
+/// Language of an example source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceLanguage {
+    /// CPU side setup and per-frame work
+    Rust,
+    /// Shaders, whether in their own file or embedded in the Rust source
+    Wgsl,
+}
+
+impl fmt::Display for SourceLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceLanguage::Rust => write!(f, "Rust"),
+            SourceLanguage::Wgsl => write!(f, "WGSL"),
+        }
+    }
+}
+
+/// A source file embedded in the binary for the example source viewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceFile {
+    /// File name shown on the viewer's tab
+    pub name: &'static str,
+    /// Language used to find the file's sections
+    pub language: SourceLanguage,
+    /// Complete file contents, embedded with `include_str!`
+    pub contents: &'static str,
+    /// Name of the item the viewer opens the file at, the top of the file if `None`
+    pub start_at: Option<&'static str>,
+}
+
+impl SourceFile {
+    /// A Rust file opened at the top
+    pub const fn rust(name: &'static str, contents: &'static str) -> Self {
+        Self {
+            name,
+            language: SourceLanguage::Rust,
+            contents,
+            start_at: None,
+        }
+    }
+
+    /// A WGSL file opened at the top
+    pub const fn wgsl(name: &'static str, contents: &'static str) -> Self {
+        Self {
+            name,
+            language: SourceLanguage::Wgsl,
+            contents,
+            start_at: None,
+        }
+    }
+
+    /// Open the file at the named function, type or constant
+    pub const fn starting_at(mut self, item: &'static str) -> Self {
+        self.start_at = Some(item);
+        self
+    }
+}
+
+/// The panel, which implements the examples without a module of their own
+const RENDERING_SOURCE: &str = include_str!("rendering.rs");
+/// Shared by the three image processing examples
+const IMAGE_PROCESSING_SOURCE: &str = include_str!("image_processing.rs");
+/// Shared by the reduction and scan examples
+const PARALLEL_ALGORITHMS_SOURCE: &str = include_str!("parallel_algorithms.rs");
+
 /// A WebGPU example with metadata and WGSL source code
 #[derive(Debug, Clone)]
 pub struct Example {
@@ -34,6 +106,8 @@ pub struct Example {
     pub description: &'static str,
     /// Complete WGSL shader source code for the example
     pub source_code: &'static str,
+    /// Rust files implementing the example on the CPU side
+    pub rust_sources: &'static [SourceFile],
 }
 
 impl Example {
@@ -44,6 +118,7 @@ impl Example {
         category: ExampleCategory,
         description: &'static str,
         source_code: &'static str,
+        rust_sources: &'static [SourceFile],
     ) -> Self {
         Self {
             id,
@@ -51,8 +126,16 @@ impl Example {
             category,
             description,
             source_code,
+            rust_sources,
         }
     }
+
+    /// The WGSL source followed by the Rust files, as shown in the source viewer
+    pub fn source_files(&self) -> Vec<SourceFile> {
+        std::iter::once(SourceFile::wgsl("shader.wgsl", self.source_code))
+            .chain(self.rust_sources.iter().copied())
+            .collect()
+    }
 }
 
 /// Get all available examples
@@ -61,7 +144,28 @@ pub fn get_all_examples() -> Vec<Example> {
         TRIANGLE_EXAMPLE.clone(),
         CUBE_EXAMPLE.clone(),
         TEXTURE_MAPPING_EXAMPLE.clone(),
+        INSTANCED_CUBES_EXAMPLE.clone(),
+        BINDLESS_MATERIALS_EXAMPLE.clone(),
+        LOD_MESHES_EXAMPLE.clone(),
+        GRASS_EXAMPLE.clone(),
+        TERRAIN_EXAMPLE.clone(),
+        TEXT_RENDERING_EXAMPLE.clone(),
+        BOIDS_EXAMPLE.clone(),
+        FLUID_EXAMPLE.clone(),
+        WATER_EXAMPLE.clone(),
+        VOLUMETRIC_FOG_EXAMPLE.clone(),
+        SHADOW_BIAS_EXAMPLE.clone(),
+        TILE_STREAMING_EXAMPLE.clone(),
+        RAY_TRACING_EXAMPLE.clone(),
+        HARDWARE_RAY_TRACING_EXAMPLE.clone(),
+        MESH_SHADING_EXAMPLE.clone(),
         COMPUTE_SHADER_EXAMPLE.clone(),
+        GAUSSIAN_BLUR_EXAMPLE.clone(),
+        SOBEL_EDGES_EXAMPLE.clone(),
+        HISTOGRAM_EQUALIZATION_EXAMPLE.clone(),
+        PARALLEL_REDUCTION_EXAMPLE.clone(),
+        PREFIX_SCAN_EXAMPLE.clone(),
+        BITONIC_SORT_EXAMPLE.clone(),
     ]
 }
 
@@ -105,6 +209,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 // Vertex 1: position (-0.5, -0.5, 0.0), color (0.0, 1.0, 0.0) - Green
 // Vertex 2: position (0.5, -0.5, 0.0), color (0.0, 0.0, 1.0) - Blue
 "#,
+    rust_sources: &[SourceFile::rust("rendering.rs", RENDERING_SOURCE)
+        .starting_at("create_triangle_render_state")],
 };
 
 /// Cube rendering example
@@ -155,6 +261,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 // Uniform buffer: Contains model, view, and projection matrices
 // The model matrix rotates over time
 "#,
+    rust_sources: &[
+        SourceFile::rust("rendering.rs", RENDERING_SOURCE).starting_at("create_cube_render_state")
+    ],
 };
 
 /// Texture mapping example
@@ -203,6 +312,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 // Sampler: Linear filtering with repeat addressing mode
 // Demonstrates bind groups for texture and sampler resources
 "#,
+    rust_sources: &[SourceFile::rust("rendering.rs", RENDERING_SOURCE)
+        .starting_at("create_texture_mapping_render_state")],
 };
 
 /// Compute shader example
@@ -246,6 +357,331 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 // - Parallel data processing
 // - GPU compute pipeline
 "#,
+    rust_sources: &[SourceFile::rust("compute.rs", include_str!("compute.rs"))
+        .starting_at("ComputePipelineDescriptor")],
+};
+
+/// Instanced rendering example with a per-instance vertex buffer
+pub static INSTANCED_CUBES_EXAMPLE: Example = Example {
+    id: "instanced_cubes",
+    name: "Instanced Cubes",
+    category: ExampleCategory::Rendering,
+    description: "Draws thousands of spinning cubes in a single call: the cube mesh is read \
+                  per vertex from one vertex buffer, each cube's position, color and spin \
+                  per instance from a second buffer with VertexStepMode::Instance. \
+                  Switch to one draw per cube to compare the CPU frame time.",
+    source_code: crate::instanced_cubes::INSTANCED_CUBES_SHADER,
+    rust_sources: &[SourceFile::rust(
+        "instanced_cubes.rs",
+        include_str!("instanced_cubes.rs"),
+    )],
+};
+
+/// Per-instance materials from a texture binding array, or an atlas without one
+pub static BINDLESS_MATERIALS_EXAMPLE: Example = Example {
+    id: "bindless_materials",
+    name: "Bindless Materials",
+    category: ExampleCategory::Rendering,
+    description: "Draws 64 cubes with 16 different textures using one bind group and one \
+                  draw call. Where the device has TEXTURE_BINDING_ARRAY and non-uniform \
+                  indexing, all textures sit in one binding_array and each fragment indexes \
+                  it with its cube's material. Elsewhere the textures are packed into an \
+                  atlas with gutters, and the panel says which path is drawing and why.",
+    source_code: crate::bindless::BINDING_ARRAY_SHADER,
+    rust_sources: &[SourceFile::rust("bindless.rs", include_str!("bindless.rs"))],
+};
+
+/// Level-of-detail meshes switched by projected size
+pub static LOD_MESHES_EXAMPLE: Example = Example {
+    id: "lod_meshes",
+    name: "LOD Mesh Switching",
+    category: ExampleCategory::Rendering,
+    description: "Draws a field of spheres with three meshes of decreasing detail, each \
+                  object using the coarsest mesh whose error projected to the screen stays \
+                  below a pixel tolerance. Select on the CPU, or cull and select in a \
+                  compute shader that writes the instanced indirect draws itself.",
+    source_code: crate::lod::LOD_SELECT_SHADER,
+    rust_sources: &[SourceFile::rust("lod.rs", include_str!("lod.rs"))],
+};
+
+/// Grass blades generated by a compute shader every frame
+pub static GRASS_EXAMPLE: Example = Example {
+    id: "compute_grass",
+    name: "Compute-Generated Grass",
+    category: ExampleCategory::Rendering,
+    description: "A compute shader writes the vertices of tens of thousands of grass blades \
+                  every frame, bending them in a travelling wind, and a render pass draws \
+                  them instanced from the same buffer. Compare alpha to coverage with an \
+                  alpha test on the blade edges under 4x MSAA.",
+    source_code: crate::grass::GRASS_GENERATE_SHADER,
+    rust_sources: &[SourceFile::rust("grass.rs", include_str!("grass.rs"))],
+};
+
+/// Noise heightmap drawn as a clipmap LOD grid built in the vertex shader
+pub static TERRAIN_EXAMPLE: Example = Example {
+    id: "terrain",
+    name: "Procedural Terrain",
+    category: ExampleCategory::Rendering,
+    description: "A compute shader sums octaves of gradient noise into a heightmap, and \
+                  the terrain is drawn without vertex buffers as rings of cells around \
+                  the camera, each twice as coarse as the one inside it. The vertex \
+                  shader builds every vertex from its index, loads its height and \
+                  reconstructs its normal from the neighbouring heights. Tune the noise \
+                  and toggle the wireframe to see the levels of detail.",
+    source_code: crate::terrain::TERRAIN_RENDER_SHADER,
+    rust_sources: &[SourceFile::rust("terrain.rs", include_str!("terrain.rs"))],
+};
+
+/// Text drawn from a glyph atlas in the example's own render pass
+pub static TEXT_RENDERING_EXAMPLE: Example = Example {
+    id: "text_rendering",
+    name: "Text Rendering",
+    category: ExampleCategory::Rendering,
+    description: "Labels anchored to points in a scene and a heads-up display, drawn \
+                  without egui: glyphs are rasterized on first use into an r8unorm atlas \
+                  texture and every character becomes an instanced quad sampling it. The \
+                  same TextRenderer can label objects in the render passes of your own \
+                  code.",
+    source_code: crate::text::TEXT_SHADER,
+    rust_sources: &[SourceFile::rust("text.rs", include_str!("text.rs"))],
+};
+
+/// Boids flocking simulated in a compute shader and drawn instanced
+pub static BOIDS_EXAMPLE: Example = Example {
+    id: "boids",
+    name: "Boids Flocking",
+    category: ExampleCategory::Rendering,
+    description: "Thousands of boids steer by cohesion, separation and alignment with \
+                  their neighbours. A compute shader reads last frame's state from one \
+                  storage buffer and writes the next to another, swapping them every \
+                  frame, and the render pass draws a triangle per boid instanced from the \
+                  written buffer. Click the preview to place obstacles.",
+    source_code: crate::boids::BOIDS_COMPUTE_SHADER,
+    rust_sources: &[SourceFile::rust("boids.rs", include_str!("boids.rs"))],
+};
+
+/// Stable-fluids style 2D fluid solved in a chain of compute dispatches
+pub static FLUID_EXAMPLE: Example = Example {
+    id: "fluid_simulation",
+    name: "2D Fluid Simulation",
+    category: ExampleCategory::Rendering,
+    description: "A stable-fluids solver on ping-pong rgba16float storage textures: \
+                  advection, divergence, Jacobi pressure iterations and gradient \
+                  subtraction run as dispatches of one compute pass, with wgpu placing the \
+                  texture barriers between them. Drag in the preview to push the fluid \
+                  and inject dye.",
+    source_code: crate::fluid::FLUID_SIMULATION_SHADER,
+    rust_sources: &[SourceFile::rust("fluid.rs", include_str!("fluid.rs"))],
+};
+
+/// Water combining planar reflection and refraction passes
+pub static WATER_EXAMPLE: Example = Example {
+    id: "water",
+    name: "Water Reflection and Refraction",
+    category: ExampleCategory::Rendering,
+    description: "The scene is rendered mirrored across the water plane into a reflection \
+                  texture and clipped below it into a refraction texture. The water surface \
+                  samples both through scrolling normal maps and blends them with the \
+                  Fresnel term, reflecting more at grazing angles.",
+    source_code: crate::water::WATER_SHADER,
+    rust_sources: &[SourceFile::rust("water.rs", include_str!("water.rs"))],
+};
+
+/// Fog raymarched through a 3D noise texture up to the scene depth
+pub static VOLUMETRIC_FOG_EXAMPLE: Example = Example {
+    id: "volumetric_fog",
+    name: "Volumetric Fog",
+    category: ExampleCategory::Rendering,
+    description: "A fullscreen pass marches a ray per pixel through a layer of fog whose \
+                  density is sampled from a tiling 3D noise texture, stopping at the point \
+                  reconstructed from the scene depth. Rays start at a blue-noise offset that \
+                  changes every frame, and the fog is blended with the reprojected previous \
+                  frame before it is composited over the scene.",
+    source_code: crate::volumetric_fog::FOG_SHADER,
+    rust_sources: &[SourceFile::rust(
+        "volumetric_fog.rs",
+        include_str!("volumetric_fog.rs"),
+    )],
+};
+
+/// Shadow map with tunable depth bias, showing acne and peter-panning
+pub static SHADOW_BIAS_EXAMPLE: Example = Example {
+    id: "shadow_bias",
+    name: "Depth Bias and Shadow Acne",
+    category: ExampleCategory::Rendering,
+    description: "A low sun casts shadows through a shadow map rendered by a depth-only \
+                  pass. Without depth bias the lit ground shadows itself in stripes; the \
+                  constant, slope scale and clamp of the shadow pipeline's depth-stencil \
+                  state push the stored depths back until the acne goes, and past that the \
+                  shadow of the thin wall comes loose from its base.",
+    source_code: crate::shadow_bias::SHADOW_SHADER,
+    rust_sources: &[SourceFile::rust(
+        "shadow_bias.rs",
+        include_str!("shadow_bias.rs"),
+    )],
+};
+
+/// Virtual texture streamed on demand from GPU feedback
+pub static TILE_STREAMING_EXAMPLE: Example = Example {
+    id: "tile_streaming",
+    name: "Tiled Resource Streaming",
+    category: ExampleCategory::Rendering,
+    description: "Textures a large ground plane from a virtual texture that never fits in \
+                  GPU memory at once. The fragment shader reports the tiles it needs, the \
+                  CPU reads the requests back without stalling and streams the missing \
+                  tiles into an atlas, evicting the least recently used ones.",
+    source_code: crate::tile_streaming::TILE_STREAMING_SHADER,
+    rust_sources: &[SourceFile::rust(
+        "tile_streaming.rs",
+        include_str!("tile_streaming.rs"),
+    )],
+};
+
+/// Path tracer in a compute shader over a BVH built on the CPU
+pub static RAY_TRACING_EXAMPLE: Example = Example {
+    id: "ray_tracing",
+    name: "Compute Ray Tracer",
+    category: ExampleCategory::Rendering,
+    description: "A path tracer written entirely in a compute shader. A glTF scene is \
+                  sorted into a bounding volume hierarchy on the CPU and uploaded as \
+                  storage buffers; every frame each pixel traces new paths through it, \
+                  with sunlight, glowing and mirror materials, and blends them into an \
+                  rgba32float storage texture holding the mean of all samples so far. \
+                  Hold the camera still to watch the noise fade, or show the BVH cost.",
+    source_code: crate::ray_tracer::RAY_TRACER_SHADER,
+    rust_sources: &[SourceFile::rust(
+        "ray_tracer.rs",
+        include_str!("ray_tracer.rs"),
+    )],
+};
+
+/// Ray queries through a TLAS, on adapters with hardware ray tracing
+pub static HARDWARE_RAY_TRACING_EXAMPLE: Example = Example {
+    id: "hardware_ray_tracing",
+    name: "Hardware Ray Queries",
+    category: ExampleCategory::Rendering,
+    description: "Traces rays with the GPU's ray tracing hardware through wgpu's experimental \
+                  ray queries. A cube, a sphere and a ground quad each get a bottom level \
+                  acceleration structure, and a top level structure places instances of \
+                  them, rebuilt every frame as the cubes move. A fragment shader casts a \
+                  camera ray and a shadow ray per pixel. Needs a Vulkan, DirectX 12 or \
+                  Metal adapter with ray tracing support; elsewhere it explains what is missing.",
+    source_code: crate::hardware_ray_tracing::RAY_QUERY_SHADER,
+    rust_sources: &[SourceFile::rust(
+        "hardware_ray_tracing.rs",
+        include_str!("hardware_ray_tracing.rs"),
+    )],
+};
+
+/// Meshlets culled in a task shader and drawn by a mesh shader
+pub static MESH_SHADING_EXAMPLE: Example = Example {
+    id: "mesh_shading",
+    name: "Mesh Shader Meshlets",
+    category: ExampleCategory::Rendering,
+    description: "Draws a dense torus knot without a vertex stage, using wgpu's experimental \
+                  mesh shaders. The model is cut into meshlets of at most 64 vertices and 124 \
+                  triangles on the CPU. A task shader culls them against the view frustum and \
+                  by their normal cone, and launches one mesh shader workgroup for each meshlet \
+                  that survives. Needs a Vulkan, DirectX 12 or Metal adapter with mesh shading \
+                  support; elsewhere it explains what is missing.",
+    source_code: crate::mesh_shading::MESH_SHADING_SHADER,
+    rust_sources: &[SourceFile::rust(
+        "mesh_shading.rs",
+        include_str!("mesh_shading.rs"),
+    )],
+};
+
+/// Gaussian blur image processing example
+pub static GAUSSIAN_BLUR_EXAMPLE: Example = Example {
+    id: "gaussian_blur",
+    name: "Gaussian Blur",
+    category: ExampleCategory::ImageProcessing,
+    description: "Blurs the loaded texture with a separable gaussian kernel in two compute \
+                  passes, reading a sampled texture and writing a storage texture. \
+                  Adjust the radius and sigma and compare before and after side by side.",
+    source_code: crate::image_processing::GAUSSIAN_BLUR_SHADER,
+    rust_sources: &[
+        SourceFile::rust("image_processing.rs", IMAGE_PROCESSING_SOURCE)
+            .starting_at("ImageProcessor"),
+    ],
+};
+
+/// Sobel edge detection image processing example
+pub static SOBEL_EDGES_EXAMPLE: Example = Example {
+    id: "sobel_edges",
+    name: "Sobel Edge Detection",
+    category: ExampleCategory::ImageProcessing,
+    description: "Finds edges in the loaded texture by convolving its luminance with the \
+                  Sobel kernels in a compute pass that writes a storage texture.",
+    source_code: crate::image_processing::SOBEL_SHADER,
+    rust_sources: &[
+        SourceFile::rust("image_processing.rs", IMAGE_PROCESSING_SOURCE)
+            .starting_at("ImageProcessor"),
+    ],
+};
+
+/// Histogram equalization image processing example
+pub static HISTOGRAM_EQUALIZATION_EXAMPLE: Example = Example {
+    id: "histogram_equalization",
+    name: "Histogram Equalization",
+    category: ExampleCategory::ImageProcessing,
+    description: "Stretches the contrast of the loaded texture: one compute pass builds a \
+                  luminance histogram with storage buffer atomics, a second turns it into a \
+                  lookup table and a third remaps every pixel into a storage texture.",
+    source_code: crate::image_processing::HISTOGRAM_EQUALIZATION_SHADER,
+    rust_sources: &[
+        SourceFile::rust("image_processing.rs", IMAGE_PROCESSING_SOURCE)
+            .starting_at("ImageProcessor"),
+    ],
+};
+
+/// Parallel reduction algorithm example
+pub static PARALLEL_REDUCTION_EXAMPLE: Example = Example {
+    id: "parallel_reduction",
+    name: "Parallel Reduction",
+    category: ExampleCategory::Algorithms,
+    description: "Sums, or finds the minimum or maximum of, millions of values. Each \
+                  workgroup combines 512 of them in var<workgroup> memory, halving the \
+                  active invocations between workgroupBarrier calls, and the pass repeats \
+                  over the partial results. Timed against a naive pass folding every \
+                  element into one global atomic, and checked against the CPU.",
+    source_code: crate::parallel_algorithms::REDUCTION_SHADER,
+    rust_sources: &[
+        SourceFile::rust("parallel_algorithms.rs", PARALLEL_ALGORITHMS_SOURCE).starting_at("Plan"),
+    ],
+};
+
+/// Prefix scan algorithm example
+pub static PREFIX_SCAN_EXAMPLE: Example = Example {
+    id: "prefix_scan",
+    name: "Prefix Scan",
+    category: ExampleCategory::Algorithms,
+    description: "Computes the running sum, minimum or maximum of millions of values. \
+                  Workgroups scan blocks of 256 in var<workgroup> memory with a \
+                  workgroupBarrier between reads and writes, the block totals are scanned \
+                  recursively and added back. Timed against a naive scan of one dispatch \
+                  per step over global memory, and checked against the CPU.",
+    source_code: crate::parallel_algorithms::PREFIX_SCAN_SHADER,
+    rust_sources: &[
+        SourceFile::rust("parallel_algorithms.rs", PARALLEL_ALGORITHMS_SOURCE).starting_at("Plan"),
+    ],
+};
+
+/// Bitonic sort example
+pub static BITONIC_SORT_EXAMPLE: Example = Example {
+    id: "bitonic_sort",
+    name: "Bitonic Sort",
+    category: ExampleCategory::Algorithms,
+    description: "Sorts millions of u32 or f32 keys with a bitonic sorting network, \
+                  a fixed schedule of compare-and-swap steps planned on the host. Steps \
+                  between nearby keys run in var<workgroup> memory, only the long ones \
+                  get a dispatch of their own. Timed against one dispatch per step and \
+                  checked against the CPU, with every step of a small sort visualized.",
+    source_code: crate::bitonic_sort::BITONIC_SORT_SHADER,
+    rust_sources: &[
+        SourceFile::rust("bitonic_sort.rs", include_str!("bitonic_sort.rs"))
+            .starting_at("schedule"),
+    ],
 };
 
 #[cfg(test)]
@@ -255,7 +691,7 @@ mod tests {
     #[test]
     fn test_all_examples_exist() {
         let examples = get_all_examples();
-        assert_eq!(examples.len(), 4);
+        assert_eq!(examples.len(), 25);
     }
 
     #[test]
@@ -291,10 +727,163 @@ mod tests {
         assert_eq!(TEXTURE_MAPPING_EXAMPLE.category, ExampleCategory::Rendering);
     }
 
+    #[test]
+    fn test_instanced_cubes_example() {
+        assert_eq!(INSTANCED_CUBES_EXAMPLE.id, "instanced_cubes");
+        assert_eq!(INSTANCED_CUBES_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(INSTANCED_CUBES_EXAMPLE
+            .source_code
+            .contains("InstanceInput"));
+    }
+
+    #[test]
+    fn test_lod_meshes_example() {
+        assert_eq!(LOD_MESHES_EXAMPLE.id, "lod_meshes");
+        assert_eq!(LOD_MESHES_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(LOD_MESHES_EXAMPLE.source_code.contains("DrawArgs"));
+    }
+
+    #[test]
+    fn test_grass_example() {
+        assert_eq!(GRASS_EXAMPLE.id, "compute_grass");
+        assert_eq!(GRASS_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(GRASS_EXAMPLE.source_code.contains("generate_blades"));
+    }
+
+    #[test]
+    fn test_terrain_example() {
+        assert_eq!(TERRAIN_EXAMPLE.id, "terrain");
+        assert_eq!(TERRAIN_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(TERRAIN_EXAMPLE.source_code.contains("covered_by_finer"));
+    }
+
+    #[test]
+    fn test_text_rendering_example() {
+        assert_eq!(TEXT_RENDERING_EXAMPLE.id, "text_rendering");
+        assert_eq!(TEXT_RENDERING_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(TEXT_RENDERING_EXAMPLE.source_code.contains("atlas_sampler"));
+    }
+
+    #[test]
+    fn test_boids_example() {
+        assert_eq!(BOIDS_EXAMPLE.id, "boids");
+        assert_eq!(BOIDS_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(BOIDS_EXAMPLE.source_code.contains("boids_out"));
+    }
+
+    #[test]
+    fn test_fluid_example() {
+        assert_eq!(FLUID_EXAMPLE.id, "fluid_simulation");
+        assert_eq!(FLUID_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(FLUID_EXAMPLE.source_code.contains("fn jacobi"));
+    }
+
+    #[test]
+    fn test_water_example() {
+        assert_eq!(WATER_EXAMPLE.id, "water");
+        assert_eq!(WATER_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(WATER_EXAMPLE.source_code.contains("fs_water"));
+    }
+
+    #[test]
+    fn test_volumetric_fog_example() {
+        assert_eq!(VOLUMETRIC_FOG_EXAMPLE.id, "volumetric_fog");
+        assert_eq!(VOLUMETRIC_FOG_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(VOLUMETRIC_FOG_EXAMPLE.source_code.contains("fs_fog"));
+    }
+
+    #[test]
+    fn test_shadow_bias_example() {
+        assert_eq!(SHADOW_BIAS_EXAMPLE.id, "shadow_bias");
+        assert_eq!(SHADOW_BIAS_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(SHADOW_BIAS_EXAMPLE.source_code.contains("vs_shadow"));
+    }
+
+    #[test]
+    fn test_tile_streaming_example() {
+        assert_eq!(TILE_STREAMING_EXAMPLE.id, "tile_streaming");
+        assert_eq!(TILE_STREAMING_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(TILE_STREAMING_EXAMPLE.source_code.contains("page_table"));
+    }
+
+    #[test]
+    fn test_ray_tracing_example() {
+        assert_eq!(RAY_TRACING_EXAMPLE.id, "ray_tracing");
+        assert_eq!(RAY_TRACING_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(RAY_TRACING_EXAMPLE.source_code.contains("trace_paths"));
+    }
+
+    #[test]
+    fn test_hardware_ray_tracing_example() {
+        assert_eq!(HARDWARE_RAY_TRACING_EXAMPLE.id, "hardware_ray_tracing");
+        assert_eq!(
+            HARDWARE_RAY_TRACING_EXAMPLE.category,
+            ExampleCategory::Rendering
+        );
+        assert!(HARDWARE_RAY_TRACING_EXAMPLE
+            .source_code
+            .contains("rayQueryInitialize"));
+    }
+
+    #[test]
+    fn test_bindless_materials_example() {
+        assert_eq!(BINDLESS_MATERIALS_EXAMPLE.id, "bindless_materials");
+        assert_eq!(
+            BINDLESS_MATERIALS_EXAMPLE.category,
+            ExampleCategory::Rendering
+        );
+        assert!(BINDLESS_MATERIALS_EXAMPLE
+            .source_code
+            .contains("binding_array<texture_2d<f32>"));
+    }
+
+    #[test]
+    fn test_mesh_shading_example() {
+        assert_eq!(MESH_SHADING_EXAMPLE.id, "mesh_shading");
+        assert_eq!(MESH_SHADING_EXAMPLE.category, ExampleCategory::Rendering);
+        assert!(MESH_SHADING_EXAMPLE.source_code.contains("@task"));
+    }
+
     #[test]
     fn test_compute_shader_example() {
         assert_eq!(COMPUTE_SHADER_EXAMPLE.id, "compute_shader");
         assert_eq!(COMPUTE_SHADER_EXAMPLE.name, "Compute Shader");
         assert_eq!(COMPUTE_SHADER_EXAMPLE.category, ExampleCategory::Compute);
     }
+
+    #[test]
+    fn test_image_processing_examples() {
+        let examples = get_all_examples();
+        let ids: Vec<&str> = examples
+            .iter()
+            .filter(|e| e.category == ExampleCategory::ImageProcessing)
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["gaussian_blur", "sobel_edges", "histogram_equalization"]
+        );
+        assert_eq!(
+            ExampleCategory::ImageProcessing.to_string(),
+            "Image Processing"
+        );
+    }
+    #[test]
+    fn test_algorithm_examples() {
+        let examples = get_all_examples();
+        let ids: Vec<&str> = examples
+            .iter()
+            .filter(|e| e.category == ExampleCategory::Algorithms)
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["parallel_reduction", "prefix_scan", "bitonic_sort"]
+        );
+        assert!(PARALLEL_REDUCTION_EXAMPLE
+            .source_code
+            .contains("workgroupBarrier"));
+        assert!(PREFIX_SCAN_EXAMPLE.source_code.contains("var<workgroup>"));
+        assert!(BITONIC_SORT_EXAMPLE.source_code.contains("fn merge_global"));
+    }
 }
@@ -0,0 +1,152 @@
+use crate::state::PlaygroundState;
+use crate::validation::{Exercise, ExerciseValidator, Outcome};
+use image::RgbaImage;
+use std::path::Path;
+
+/// UI panel that evaluates an exercise file against the active workspace,
+/// with pass/fail feedback updated as the panels change
+pub struct ExercisePanel {
+    path: String,
+    exercise: Option<Exercise>,
+    validator: ExerciseValidator,
+    status_message: Option<String>,
+}
+
+impl Default for ExercisePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExercisePanel {
+    /// Create a new panel with no exercise loaded
+    pub fn new() -> Self {
+        Self {
+            path: String::new(),
+            exercise: None,
+            validator: ExerciseValidator::new(),
+            status_message: None,
+        }
+    }
+
+    /// Load an exercise file
+    pub fn load(&mut self, path: &Path) -> Result<(), String> {
+        let exercise = Exercise::load_from_file(path).map_err(|e| e.to_string())?;
+        self.exercise = Some(exercise);
+        Ok(())
+    }
+
+    pub fn exercise(&self) -> Option<&Exercise> {
+        self.exercise.as_ref()
+    }
+
+    /// Use a captured frame for the checks on the rendered output
+    pub fn set_rendered_output(&mut self, image: RgbaImage) {
+        self.validator.set_rendered(image);
+    }
+
+    /// Render the panel UI, checking the exercise against `live`
+    pub fn ui(&mut self, ui: &mut egui::Ui, live: &PlaygroundState) {
+        ui.heading("✅ Exercise Checks");
+        ui.label(
+            "Load an exercise file listing assertions about the playground state and the \
+             rendered output, and see which of them hold as you edit.",
+        );
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Exercise:");
+            ui.text_edit_singleline(&mut self.path)
+                .on_hover_text("JSON file with a title and a list of checks");
+            if ui.button("Load").clicked() {
+                let path = self.path.trim().to_string();
+                self.status_message = Some(match self.load(Path::new(&path)) {
+                    Ok(()) => format!("✓ Loaded {}", path),
+                    Err(e) => format!("✗ {}", e),
+                });
+            }
+        });
+        if let Some(message) = &self.status_message {
+            let color = if message.starts_with('✓') {
+                egui::Color32::from_rgb(100, 200, 100)
+            } else {
+                egui::Color32::from_rgb(220, 80, 80)
+            };
+            ui.colored_label(color, message);
+        }
+        if !self.validator.has_rendered() {
+            ui.weak("Tip: press F12 or 📸 Capture to check the rendered output.");
+        }
+        ui.add_space(8.0);
+
+        let Some(exercise) = &self.exercise else {
+            return;
+        };
+        let outcomes = self.validator.evaluate(&exercise.checks, live);
+        let passed = outcomes
+            .iter()
+            .filter(|outcome| outcome.is_passed())
+            .count();
+
+        ui.group(|ui| {
+            ui.heading(&exercise.title);
+            if !exercise.description.is_empty() {
+                ui.label(&exercise.description);
+            }
+            ui.add(
+                egui::ProgressBar::new(passed as f32 / outcomes.len().max(1) as f32).text(format!(
+                    "{} / {} checks passing",
+                    passed,
+                    outcomes.len()
+                )),
+            );
+            ui.add_space(4.0);
+
+            egui::Grid::new("exercise_checks")
+                .num_columns(3)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    for (check, outcome) in exercise.checks.iter().zip(&outcomes) {
+                        let (icon, color, detail) = match outcome {
+                            Outcome::Passed => ("✓", egui::Color32::from_rgb(100, 200, 100), None),
+                            Outcome::Failed(detail) => {
+                                ("✗", egui::Color32::from_rgb(220, 80, 80), Some(detail))
+                            }
+                            Outcome::Pending(reason) => ("⏳", egui::Color32::GRAY, Some(reason)),
+                        };
+                        ui.colored_label(color, icon);
+                        ui.label(&check.description);
+                        match (outcome, &check.hint) {
+                            (Outcome::Failed(_), Some(hint)) => {
+                                ui.weak(format!("💡 {}", hint))
+                                    .on_hover_text(detail.map(String::as_str).unwrap_or_default());
+                            }
+                            _ => {
+                                ui.weak(detail.map(String::as_str).unwrap_or_default());
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            if passed == outcomes.len() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(100, 200, 100),
+                    "✓ Every check passes",
+                );
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_reports_missing_files() {
+        let mut panel = ExercisePanel::new();
+        assert!(panel.load(Path::new("/nonexistent/exercise.json")).is_err());
+        assert!(panel.exercise().is_none());
+    }
+}
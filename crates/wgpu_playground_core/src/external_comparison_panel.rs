@@ -0,0 +1,463 @@
+use crate::device_recovery::ResourceRecreate;
+use crate::frame_capture::to_color_image;
+use crate::visual_regression::external::{
+    align_external, auto_align, compare_external, load_external_image, AlignmentSettings, CropRect,
+    ExternalComparison,
+};
+use image::RgbaImage;
+use std::path::Path;
+
+/// Maximum shift searched by the auto-align button, in pixels
+const AUTO_ALIGN_MAX_SHIFT: u32 = 16;
+
+/// Which image is shown in the preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreviewMode {
+    Playground,
+    External,
+    Diff,
+}
+
+/// UI panel for comparing playground output against images produced by
+/// other implementations (native samples, browser screenshots)
+pub struct ExternalComparisonPanel {
+    playground_path: String,
+    external_path: String,
+    diff_path: String,
+    playground_image: Option<RgbaImage>,
+    external_image: Option<RgbaImage>,
+    use_crop: bool,
+    crop: CropRect,
+    settings: AlignmentSettings,
+    /// Threshold on the mean difference for a pass (0.0 - 1.0)
+    threshold: f32,
+    /// Per-pixel tolerance for counting mismatched pixels (0.0 - 1.0)
+    pixel_tolerance: f32,
+    ignore_alpha: bool,
+    result: Option<ExternalComparison>,
+    preview_mode: PreviewMode,
+    preview_texture: Option<egui::TextureHandle>,
+    preview_dirty: bool,
+    status_message: Option<String>,
+}
+
+impl Default for ExternalComparisonPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExternalComparisonPanel {
+    /// Create a new panel with no images loaded
+    pub fn new() -> Self {
+        Self {
+            playground_path: String::new(),
+            external_path: String::new(),
+            diff_path: "external_diff.png".to_string(),
+            playground_image: None,
+            external_image: None,
+            use_crop: false,
+            crop: CropRect {
+                x: 0,
+                y: 0,
+                width: 1,
+                height: 1,
+            },
+            settings: AlignmentSettings::default(),
+            threshold: 0.01,
+            pixel_tolerance: 0.02,
+            ignore_alpha: true,
+            result: None,
+            preview_mode: PreviewMode::Diff,
+            preview_texture: None,
+            preview_dirty: true,
+            status_message: None,
+        }
+    }
+
+    /// Use a captured frame (e.g. the latest screenshot) as playground image
+    pub fn set_playground_image(&mut self, image: RgbaImage) {
+        self.playground_image = Some(image);
+        self.result = None;
+        self.preview_dirty = true;
+    }
+
+    /// Whether a playground image is available
+    pub fn has_playground_image(&self) -> bool {
+        self.playground_image.is_some()
+    }
+
+    /// Load the external image and reset the crop to its full size
+    pub fn load_external(&mut self, path: &Path) -> Result<(), String> {
+        let image = load_external_image(path).map_err(|e| e.to_string())?;
+        self.crop = CropRect {
+            x: 0,
+            y: 0,
+            width: image.width(),
+            height: image.height(),
+        };
+        self.external_image = Some(image);
+        self.result = None;
+        self.preview_dirty = true;
+        Ok(())
+    }
+
+    /// Load the playground image from a file instead of a captured frame
+    pub fn load_playground(&mut self, path: &Path) -> Result<(), String> {
+        let image = load_external_image(path).map_err(|e| e.to_string())?;
+        self.set_playground_image(image);
+        Ok(())
+    }
+
+    fn alignment(&self) -> AlignmentSettings {
+        AlignmentSettings {
+            crop: self.use_crop.then_some(self.crop),
+            ..self.settings.clone()
+        }
+    }
+
+    fn aligned_external(&self) -> Option<(&RgbaImage, RgbaImage)> {
+        let playground = self.playground_image.as_ref()?;
+        let external = self.external_image.as_ref()?;
+        Some((
+            playground,
+            align_external(external, playground.dimensions(), &self.alignment()),
+        ))
+    }
+
+    /// Run the comparison with the current alignment settings
+    pub fn compare(&mut self) -> Option<&ExternalComparison> {
+        let (playground, aligned) = self.aligned_external()?;
+        let result = compare_external(
+            playground,
+            &aligned,
+            self.settings.offset,
+            self.pixel_tolerance,
+            self.ignore_alpha,
+        );
+        self.result = Some(result);
+        self.preview_dirty = true;
+        self.result.as_ref()
+    }
+
+    /// Search for the offset that best aligns the images
+    pub fn auto_align(&mut self) -> Option<(i32, i32)> {
+        let (playground, aligned) = self.aligned_external()?;
+        let offset = auto_align(playground, &aligned, AUTO_ALIGN_MAX_SHIFT);
+        self.settings.offset = offset;
+        Some(offset)
+    }
+
+    /// Render the panel UI
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🔍 External Reference Comparison");
+        ui.label(
+            "Compare the playground's output against an image produced by another \
+             implementation, e.g. a native D3D/Vulkan sample or a browser screenshot.",
+        );
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            self.render_inputs(ui);
+            ui.add_space(8.0);
+            self.render_alignment(ui);
+            ui.add_space(8.0);
+            self.render_comparison(ui);
+            ui.add_space(8.0);
+            self.render_preview(ui);
+        });
+    }
+
+    fn render_inputs(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.heading("🖼️ Images");
+            egui::Grid::new("external_comparison_inputs")
+                .num_columns(3)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Playground:");
+                    ui.text_edit_singleline(&mut self.playground_path)
+                        .on_hover_text(
+                            "PNG/JPEG of the playground output, or take a screenshot (F12)",
+                        );
+                    if ui.button("Load").clicked() {
+                        let path = self.playground_path.trim().to_string();
+                        self.status_message = Some(match self.load_playground(Path::new(&path)) {
+                            Ok(()) => format!("✓ Loaded playground image {}", path),
+                            Err(e) => format!("✗ {}", e),
+                        });
+                    }
+                    ui.end_row();
+
+                    ui.label("External:");
+                    ui.text_edit_singleline(&mut self.external_path)
+                        .on_hover_text("Image produced by the other implementation");
+                    if ui.button("Load").clicked() {
+                        let path = self.external_path.trim().to_string();
+                        self.status_message = Some(match self.load_external(Path::new(&path)) {
+                            Ok(()) => format!("✓ Loaded external image {}", path),
+                            Err(e) => format!("✗ {}", e),
+                        });
+                    }
+                    ui.end_row();
+                });
+
+            let describe = |image: &Option<RgbaImage>| match image {
+                Some(image) => format!("{}×{}", image.width(), image.height()),
+                None => "not loaded".to_string(),
+            };
+            ui.label(format!(
+                "Playground: {}   External: {}",
+                describe(&self.playground_image),
+                describe(&self.external_image)
+            ));
+            if self.playground_image.is_none() {
+                ui.weak("Tip: press F12 or 📸 Capture to use the current frame.");
+            }
+
+            if let Some(message) = &self.status_message {
+                let color = if message.starts_with('✓') {
+                    egui::Color32::from_rgb(100, 200, 100)
+                } else {
+                    egui::Color32::from_rgb(220, 80, 80)
+                };
+                ui.colored_label(color, message);
+            }
+        });
+    }
+
+    fn render_alignment(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.heading("📐 Alignment");
+
+            let (max_w, max_h) = self
+                .external_image
+                .as_ref()
+                .map(|image| image.dimensions())
+                .unwrap_or((1, 1));
+            ui.checkbox(&mut self.use_crop, "Crop external image")
+                .on_hover_text("Remove window chrome or borders before comparing");
+            ui.add_enabled_ui(self.use_crop, |ui| {
+                egui::Grid::new("external_comparison_crop")
+                    .num_columns(4)
+                    .spacing([10.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("X:");
+                        ui.add(egui::DragValue::new(&mut self.crop.x).range(0..=max_w - 1));
+                        ui.label("Y:");
+                        ui.add(egui::DragValue::new(&mut self.crop.y).range(0..=max_h - 1));
+                        ui.end_row();
+                        ui.label("Width:");
+                        ui.add(egui::DragValue::new(&mut self.crop.width).range(1..=max_w));
+                        ui.label("Height:");
+                        ui.add(egui::DragValue::new(&mut self.crop.height).range(1..=max_h));
+                        ui.end_row();
+                    });
+            });
+
+            ui.checkbox(
+                &mut self.settings.resize_to_match,
+                "Resize to playground size",
+            )
+            .on_hover_text("Rescale the (cropped) external image, e.g. for HiDPI screenshots");
+
+            ui.horizontal(|ui| {
+                ui.label("Offset:");
+                ui.add(egui::DragValue::new(&mut self.settings.offset.0).prefix("dx "));
+                ui.add(egui::DragValue::new(&mut self.settings.offset.1).prefix("dy "));
+                let ready = self.playground_image.is_some() && self.external_image.is_some();
+                if ui
+                    .add_enabled(ready, egui::Button::new("🎯 Auto-align"))
+                    .on_hover_text(format!(
+                        "Search shifts up to ±{} pixels for the best match",
+                        AUTO_ALIGN_MAX_SHIFT
+                    ))
+                    .clicked()
+                {
+                    if let Some((dx, dy)) = self.auto_align() {
+                        self.status_message = Some(format!("✓ Best offset: ({}, {})", dx, dy));
+                        self.compare();
+                    }
+                }
+                if ui.button("Reset").clicked() {
+                    self.settings.offset = (0, 0);
+                }
+            });
+        });
+    }
+
+    fn render_comparison(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.heading("📊 Comparison");
+            ui.add(
+                egui::Slider::new(&mut self.threshold, 0.0..=0.2)
+                    .text("Pass threshold")
+                    .fixed_decimals(3),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.pixel_tolerance, 0.0..=0.5)
+                    .text("Per-pixel tolerance")
+                    .fixed_decimals(3),
+            );
+            ui.checkbox(&mut self.ignore_alpha, "Ignore alpha channel");
+
+            let ready = self.playground_image.is_some() && self.external_image.is_some();
+            if ui
+                .add_enabled(ready, egui::Button::new("▶ Compare"))
+                .clicked()
+            {
+                self.compare();
+            }
+
+            let Some(result) = &self.result else {
+                return;
+            };
+            let comparison = result.to_comparison_result(self.threshold);
+            if comparison.is_match {
+                ui.colored_label(
+                    egui::Color32::from_rgb(100, 200, 100),
+                    "✓ Images match within the threshold",
+                );
+            } else {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 80, 80),
+                    "✗ Images differ beyond the threshold",
+                );
+            }
+
+            egui::Grid::new("external_comparison_metrics")
+                .num_columns(2)
+                .spacing([20.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Mean difference:");
+                    ui.label(format!("{:.4}%", result.difference * 100.0));
+                    ui.end_row();
+                    ui.label("PSNR:");
+                    if result.psnr.is_finite() {
+                        ui.label(format!("{:.2} dB", result.psnr));
+                    } else {
+                        ui.label("∞ (identical)");
+                    }
+                    ui.end_row();
+                    ui.label("Max channel difference:");
+                    ui.label(result.max_channel_difference.to_string());
+                    ui.end_row();
+                    ui.label("Mismatched pixels:");
+                    ui.label(format!(
+                        "{} ({:.2}%)",
+                        result.mismatched_pixels,
+                        result.mismatched_fraction() * 100.0
+                    ));
+                    ui.end_row();
+                    ui.label("Coverage:");
+                    ui.label(format!("{:.1}%", result.coverage() * 100.0));
+                    ui.end_row();
+                });
+
+            ui.horizontal(|ui| {
+                ui.label("Diff image:");
+                ui.text_edit_singleline(&mut self.diff_path);
+                if ui.button("💾 Save").clicked() {
+                    let path = self.diff_path.trim().to_string();
+                    self.status_message = Some(match result.diff_image.save(&path) {
+                        Ok(()) => format!("✓ Saved diff image to {}", path),
+                        Err(e) => format!("✗ Failed to save {}: {}", path, e),
+                    });
+                }
+            });
+        });
+    }
+
+    fn render_preview(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.heading("👁️ Preview");
+            ui.horizontal(|ui| {
+                for (mode, label) in [
+                    (PreviewMode::Playground, "Playground"),
+                    (PreviewMode::External, "External (aligned)"),
+                    (PreviewMode::Diff, "Difference"),
+                ] {
+                    if ui
+                        .selectable_value(&mut self.preview_mode, mode, label)
+                        .changed()
+                    {
+                        self.preview_dirty = true;
+                    }
+                }
+            });
+
+            if self.preview_dirty {
+                let image = match self.preview_mode {
+                    PreviewMode::Playground => self.playground_image.clone(),
+                    PreviewMode::External => self.aligned_external().map(|(_, aligned)| aligned),
+                    PreviewMode::Diff => self.result.as_ref().map(|r| r.diff_image.clone()),
+                };
+                self.preview_texture = image.map(|image| {
+                    ui.ctx().load_texture(
+                        "external_comparison_preview",
+                        to_color_image(&image),
+                        egui::TextureOptions::NEAREST,
+                    )
+                });
+                self.preview_dirty = false;
+            }
+
+            match &self.preview_texture {
+                Some(texture) => {
+                    let size = texture.size_vec2();
+                    let scale = (ui.available_width() / size.x).min(1.0);
+                    ui.image((texture.id(), size * scale));
+                    if self.preview_mode == PreviewMode::Diff {
+                        ui.weak(
+                            "Red: difference intensity. Blue: not covered by the external image.",
+                        );
+                    }
+                }
+                None => {
+                    ui.weak("Nothing to show yet.");
+                }
+            }
+        });
+    }
+}
+
+impl ResourceRecreate for ExternalComparisonPanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        self.preview_texture = None;
+        self.preview_dirty = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_compare_and_auto_align() {
+        let mut panel = ExternalComparisonPanel::new();
+        assert!(panel.compare().is_none());
+
+        let playground = RgbaImage::from_fn(32, 32, |x, y| {
+            if (10..18).contains(&x) && (8..16).contains(&y) {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+        let external = RgbaImage::from_fn(32, 32, |x, y| {
+            if (8..16).contains(&x) && (8..16).contains(&y) {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+        panel.set_playground_image(playground);
+        panel.external_image = Some(external);
+
+        assert!(panel.compare().unwrap().difference > 0.0);
+        assert_eq!(panel.auto_align(), Some((2, 0)));
+        assert_eq!(panel.compare().unwrap().difference, 0.0);
+    }
+}
@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{channel, Receiver};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, Mutex};
+
+/// Watches an explicit set of files anywhere on disk
+///
+/// Used for files outside the assets directory, such as a project file and
+/// the shader it references. Each file's parent directory is watched, so a
+/// file that editors replace on save (write to a temporary file, then
+/// rename) keeps being reported. Only paths in the set are reported.
+/// On WASM platforms, the watcher can be created but will not detect any changes.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileWatcher {
+    _watcher: notify::RecommendedWatcher,
+    receiver: Arc<Mutex<Receiver<PathBuf>>>,
+    files: Vec<PathBuf>,
+}
+
+/// Type alias for the result type returned by FileWatcher operations
+type WatcherResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// Absolute form of a path without requiring it to exist
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether `path` refers to one of `files`, comparing absolute paths
+pub fn is_watched(files: &[PathBuf], path: &Path) -> bool {
+    let path = absolute(path);
+    files.iter().any(|file| *file == path)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileWatcher {
+    /// Watch the given files
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use wgpu_playground_core::file_watcher::FileWatcher;
+    ///
+    /// let watcher = FileWatcher::watch(&["project.json".into()]).unwrap();
+    /// for path in watcher.poll_all() {
+    ///     println!("Changed: {}", path.display());
+    /// }
+    /// ```
+    pub fn watch(files: &[PathBuf]) -> WatcherResult<Self> {
+        use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+        let (tx, rx) = channel();
+        let tx = Arc::new(Mutex::new(tx));
+
+        let files: Vec<PathBuf> = files.iter().map(|file| absolute(file)).collect();
+        let matched = files.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |res: Result<Event, notify::Error>| match res {
+                Ok(event) => {
+                    if !matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        return;
+                    }
+                    for path in event.paths {
+                        if is_watched(&matched, &path) {
+                            log::info!("Detected file change: {}", path.display());
+                            if let Ok(tx) = tx.lock() {
+                                let _ = tx.send(path);
+                            }
+                        }
+                    }
+                }
+                Err(e) => log::error!("File watcher error: {:?}", e),
+            })?;
+
+        let mut dirs: Vec<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.parent().map(Path::to_path_buf))
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        for dir in &dirs {
+            log::info!("Starting file watcher for directory: {:?}", dir);
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver: Arc::new(Mutex::new(rx)),
+            files,
+        })
+    }
+
+    /// Absolute paths of the watched files
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Poll for a changed file (non-blocking)
+    pub fn poll(&self) -> Option<PathBuf> {
+        if let Ok(rx) = self.receiver.lock() {
+            rx.try_recv().ok()
+        } else {
+            None
+        }
+    }
+
+    /// Get all pending changes, each changed file once
+    pub fn poll_all(&self) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = Vec::new();
+        while let Some(path) = self.poll() {
+            if !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+        changed
+    }
+}
+
+// WASM stub implementation
+/// WASM stub that provides the same API but without file watching functionality
+#[cfg(target_arch = "wasm32")]
+pub struct FileWatcher {
+    files: Vec<PathBuf>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl FileWatcher {
+    /// Create a watcher for files (WASM stub - does nothing)
+    pub fn watch(files: &[PathBuf]) -> WatcherResult<Self> {
+        Ok(Self {
+            files: files.to_vec(),
+        })
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn poll(&self) -> Option<PathBuf> {
+        None
+    }
+
+    pub fn poll_all(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_watched() {
+        let dir = std::env::temp_dir();
+        let files = vec![absolute(&dir.join("watched.wgsl"))];
+        assert!(is_watched(&files, &dir.join("watched.wgsl")));
+        assert!(is_watched(&files, &dir.join(".").join("watched.wgsl")));
+        assert!(!is_watched(&files, &dir.join("other.wgsl")));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_file_watcher_reports_changes() {
+        let dir = std::env::temp_dir().join("wgpu_playground_file_watcher_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let watched = dir.join("watched.txt");
+        let ignored = dir.join("ignored.txt");
+        std::fs::write(&watched, "a").unwrap();
+
+        let watcher = FileWatcher::watch(std::slice::from_ref(&watched)).unwrap();
+        std::fs::write(&ignored, "b").unwrap();
+        std::fs::write(&watched, "b").unwrap();
+
+        let mut changed = Vec::new();
+        for _ in 0..50 {
+            changed.extend(watcher.poll_all());
+            if !changed.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        // Some CI file systems deliver no events; only check what arrived
+        assert!(changed.iter().all(|path| path.ends_with("watched.txt")));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
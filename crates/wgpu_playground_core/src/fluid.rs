@@ -0,0 +1,932 @@
+//! 2D fluid simulation in the style of Stam's stable fluids
+//!
+//! Velocity, dye and pressure live in `rgba16float` textures, each kept
+//! twice: a stage samples one copy and writes the other through a storage
+//! binding, then the two swap roles. Every frame runs these stages as
+//! dispatches of one compute pass:
+//!
+//! 1. Splat: add the force and dye of a pointer drag around a point
+//! 2. Advect: carry velocity and dye along the velocity, sampling where each
+//!    cell's content came from one time step ago
+//! 3. Divergence: how much the velocity flows out of each cell
+//! 4. Jacobi iterations: relax a pressure field whose gradient cancels
+//!    that divergence
+//! 5. Gradient subtract: remove the pressure gradient, leaving a velocity
+//!    field that neither gains nor loses fluid anywhere
+//!
+//! Every dispatch is its own usage scope, so wgpu inserts the texture
+//! barriers between a stage writing a texture and the next stage sampling
+//! it. A render pass then shows the dye, the velocity or the pressure.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::upload_belt;
+use std::collections::VecDeque;
+
+/// Cells along the height of the grid; the width follows the aspect ratio
+pub const GRID_HEIGHT: u32 = 256;
+
+/// Most Jacobi iterations of the pressure solve per frame
+pub const MAX_PRESSURE_ITERATIONS: u32 = 100;
+
+const WORKGROUP_SIZE: u32 = 8;
+
+const FIELD_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+pub const FLUID_SIMULATION_SHADER: &str = r#"// 2D Fluid Simulation Example
+// Every entry point samples `field_a` and `field_b` and writes `output`,
+// one invocation per grid cell. Velocity is in cells per second.
+
+struct Params {
+    texel: vec2<f32>,
+    delta_time: f32,
+    dye_dissipation: f32,
+    // Center of the splat in texture coordinates
+    splat_point: vec2<f32>,
+    splat_force: vec2<f32>,
+    splat_color: vec4<f32>,
+    // Radius of the splat as a fraction of the grid height
+    splat_radius: f32,
+    velocity_dissipation: f32,
+    view: u32,
+    _padding: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var linear_sampler: sampler;
+@group(0) @binding(2) var field_a: texture_2d<f32>;
+@group(0) @binding(3) var field_b: texture_2d<f32>;
+@group(0) @binding(4) var output: texture_storage_2d<rgba16float, write>;
+
+fn grid_size() -> vec2<i32> {
+    return vec2<i32>(textureDimensions(output));
+}
+
+// Neighbours clamp to the edge, so the walls reflect pressure
+fn load(field: texture_2d<f32>, cell: vec2<i32>) -> vec4<f32> {
+    return textureLoad(field, clamp(cell, vec2<i32>(0), grid_size() - 1), 0);
+}
+
+fn uv(cell: vec2<i32>) -> vec2<f32> {
+    return (vec2<f32>(cell) + 0.5) * params.texel;
+}
+
+fn splat_weight(cell: vec2<i32>) -> f32 {
+    let aspect = params.texel.y / params.texel.x;
+    let offset = (uv(cell) - params.splat_point) * vec2<f32>(aspect, 1.0);
+    return exp(-dot(offset, offset) / (params.splat_radius * params.splat_radius));
+}
+
+@compute @workgroup_size(8, 8)
+fn splat_velocity(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = vec2<i32>(id.xy);
+    if any(cell >= grid_size()) {
+        return;
+    }
+    let velocity = load(field_a, cell).xy + params.splat_force * splat_weight(cell);
+    textureStore(output, cell, vec4<f32>(velocity, 0.0, 1.0));
+}
+
+@compute @workgroup_size(8, 8)
+fn splat_dye(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = vec2<i32>(id.xy);
+    if any(cell >= grid_size()) {
+        return;
+    }
+    let dye = load(field_a, cell) + params.splat_color * splat_weight(cell);
+    textureStore(output, cell, min(dye, vec4<f32>(4.0)));
+}
+
+// Sample `field_b` where the content of the cell was one step ago
+fn advect(cell: vec2<i32>, dissipation: f32) -> vec4<f32> {
+    let velocity = load(field_a, cell).xy;
+    let source = uv(cell) - velocity * params.delta_time * params.texel;
+    let value = textureSampleLevel(field_b, linear_sampler, source, 0.0);
+    return value / (1.0 + dissipation * params.delta_time);
+}
+
+@compute @workgroup_size(8, 8)
+fn advect_velocity(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = vec2<i32>(id.xy);
+    if any(cell >= grid_size()) {
+        return;
+    }
+    textureStore(output, cell, advect(cell, params.velocity_dissipation));
+}
+
+@compute @workgroup_size(8, 8)
+fn advect_dye(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = vec2<i32>(id.xy);
+    if any(cell >= grid_size()) {
+        return;
+    }
+    textureStore(output, cell, advect(cell, params.dye_dissipation));
+}
+
+@compute @workgroup_size(8, 8)
+fn divergence(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = vec2<i32>(id.xy);
+    if any(cell >= grid_size()) {
+        return;
+    }
+    let left = load(field_a, cell - vec2<i32>(1, 0)).x;
+    let right = load(field_a, cell + vec2<i32>(1, 0)).x;
+    let up = load(field_a, cell - vec2<i32>(0, 1)).y;
+    let down = load(field_a, cell + vec2<i32>(0, 1)).y;
+    textureStore(output, cell, vec4<f32>(0.5 * (right - left + down - up), 0.0, 0.0, 1.0));
+}
+
+// One iteration towards the pressure whose Laplacian is the divergence
+@compute @workgroup_size(8, 8)
+fn jacobi(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = vec2<i32>(id.xy);
+    if any(cell >= grid_size()) {
+        return;
+    }
+    let left = load(field_a, cell - vec2<i32>(1, 0)).x;
+    let right = load(field_a, cell + vec2<i32>(1, 0)).x;
+    let up = load(field_a, cell - vec2<i32>(0, 1)).x;
+    let down = load(field_a, cell + vec2<i32>(0, 1)).x;
+    let divergence = load(field_b, cell).x;
+    let pressure = (left + right + up + down - divergence) * 0.25;
+    textureStore(output, cell, vec4<f32>(pressure, 0.0, 0.0, 1.0));
+}
+
+@compute @workgroup_size(8, 8)
+fn subtract_gradient(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = vec2<i32>(id.xy);
+    if any(cell >= grid_size()) {
+        return;
+    }
+    let left = load(field_a, cell - vec2<i32>(1, 0)).x;
+    let right = load(field_a, cell + vec2<i32>(1, 0)).x;
+    let up = load(field_a, cell - vec2<i32>(0, 1)).x;
+    let down = load(field_a, cell + vec2<i32>(0, 1)).x;
+    let velocity = load(field_b, cell).xy - 0.5 * vec2<f32>(right - left, down - up);
+    textureStore(output, cell, vec4<f32>(velocity, 0.0, 1.0));
+}
+"#;
+
+pub const FLUID_DISPLAY_SHADER: &str = r#"// Fluid display: a full-screen triangle sampling the chosen field
+
+struct Params {
+    texel: vec2<f32>,
+    delta_time: f32,
+    dye_dissipation: f32,
+    splat_point: vec2<f32>,
+    splat_force: vec2<f32>,
+    splat_color: vec4<f32>,
+    splat_radius: f32,
+    velocity_dissipation: f32,
+    view: u32,
+    _padding: u32,
+}
+
+const VIEW_DYE: u32 = 0u;
+const VIEW_VELOCITY: u32 = 1u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var linear_sampler: sampler;
+@group(0) @binding(2) var field: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let value = textureSampleLevel(field, linear_sampler, in.uv, 0.0);
+    if params.view == VIEW_DYE {
+        return vec4<f32>(min(value.rgb, vec3<f32>(1.0)), 1.0);
+    }
+    if params.view == VIEW_VELOCITY {
+        // Direction as hue around gray, brighter the faster
+        return vec4<f32>(clamp(0.5 + value.xy * 0.01, vec2<f32>(0.0), vec2<f32>(1.0)), 0.5, 1.0);
+    }
+    // Pressure: red above zero, blue below
+    let pressure = clamp(value.x * 0.05, -1.0, 1.0);
+    return vec4<f32>(max(pressure, 0.0), 0.0, max(-pressure, 0.0), 1.0);
+}
+"#;
+
+/// The field the preview shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluidView {
+    Dye,
+    Velocity,
+    Pressure,
+}
+
+impl FluidView {
+    pub const ALL: [FluidView; 3] = [FluidView::Dye, FluidView::Velocity, FluidView::Pressure];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FluidView::Dye => "Dye",
+            FluidView::Velocity => "Velocity",
+            FluidView::Pressure => "Pressure",
+        }
+    }
+
+    /// Matches the `VIEW_` constants of the display shader
+    fn shader_value(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// Adjustable parameters of the solver
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FluidParams {
+    /// Jacobi iterations of the pressure solve, up to [`MAX_PRESSURE_ITERATIONS`]
+    pub pressure_iterations: u32,
+    /// How fast dye fades, per second
+    pub dye_dissipation: f32,
+    /// How fast velocity fades, per second
+    pub velocity_dissipation: f32,
+    /// Radius of a splat as a fraction of the grid height
+    pub splat_radius: f32,
+    /// Multiplier of the force a pointer drag applies
+    pub splat_force: f32,
+}
+
+impl Default for FluidParams {
+    fn default() -> Self {
+        Self {
+            pressure_iterations: 40,
+            dye_dissipation: 0.3,
+            velocity_dissipation: 0.2,
+            splat_radius: 0.04,
+            splat_force: 1.0,
+        }
+    }
+}
+
+/// Force and dye added around a point in one frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Splat {
+    /// Texture coordinates, from the top left
+    point: [f32; 2],
+    /// Cells per second
+    force: [f32; 2],
+    color: [f32; 3],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    texel: [f32; 2],
+    delta_time: f32,
+    dye_dissipation: f32,
+    splat_point: [f32; 2],
+    splat_force: [f32; 2],
+    splat_color: [f32; 4],
+    splat_radius: f32,
+    velocity_dissipation: f32,
+    view: u32,
+    _padding: u32,
+}
+
+/// A saturated color of the given hue, 0 to 1 around the color wheel
+pub fn hue_color(hue: f32) -> [f32; 3] {
+    let channel = |offset: f32| {
+        let x = (hue + offset).rem_euclid(1.0) * 6.0;
+        (x - 3.0).abs().clamp(1.0, 2.0) - 1.0
+    };
+    [channel(0.0), channel(2.0 / 3.0), channel(1.0 / 3.0)]
+}
+
+/// Size of the grid for a preview of the given width to height ratio
+pub fn grid_size(aspect: f32) -> (u32, u32) {
+    let width = (GRID_HEIGHT as f32 * aspect).round() as u32;
+    (width.clamp(1, GRID_HEIGHT * 4), GRID_HEIGHT)
+}
+
+/// A field kept twice, sampled from one copy and stored into the other
+struct PingPong {
+    textures: [wgpu::Texture; 2],
+    views: [wgpu::TextureView; 2],
+    /// Index of the copy holding the latest values
+    current: usize,
+}
+
+impl PingPong {
+    fn new(device: &wgpu::Device, label: &str, (width, height): (u32, u32)) -> Self {
+        let textures = [0, 1].map(|index| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(&format!("{} {}", label, index)),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: FIELD_FORMAT,
+                // Render attachment only to clear it on reset
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::STORAGE_BINDING
+                    | wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            })
+        });
+        let views = [0, 1]
+            .map(|index| textures[index].create_view(&wgpu::TextureViewDescriptor::default()));
+        Self {
+            textures,
+            views,
+            current: 0,
+        }
+    }
+
+    fn swap(&mut self) {
+        self.current = 1 - self.current;
+    }
+}
+
+/// Simulation and renderer of the fluid example
+pub struct FluidSim {
+    splat_velocity: wgpu::ComputePipeline,
+    splat_dye: wgpu::ComputePipeline,
+    advect_velocity: wgpu::ComputePipeline,
+    advect_dye: wgpu::ComputePipeline,
+    divergence: wgpu::ComputePipeline,
+    jacobi: wgpu::ComputePipeline,
+    subtract_gradient: wgpu::ComputePipeline,
+    display_pipeline: wgpu::RenderPipeline,
+    velocity: PingPong,
+    dye: PingPong,
+    pressure: PingPong,
+    divergence_texture: wgpu::Texture,
+    /// Velocity `[v]` into the other velocity, for splats and advection
+    velocity_steps: [wgpu::BindGroup; 2],
+    /// Dye `[d]` into the other dye, for splats
+    dye_steps: [wgpu::BindGroup; 2],
+    /// Dye `[d]` advected by velocity `[v]`, as `[v][d]`
+    advect_dye_steps: [[wgpu::BindGroup; 2]; 2],
+    /// Divergence of velocity `[v]`
+    divergence_steps: [wgpu::BindGroup; 2],
+    /// Pressure `[p]` into the other pressure
+    jacobi_steps: [wgpu::BindGroup; 2],
+    /// Gradient of pressure `[p]` subtracted from velocity `[v]`, as `[p][v]`
+    gradient_steps: [[wgpu::BindGroup; 2]; 2],
+    /// Both copies of the dye, velocity and pressure, by [`FluidView`]
+    display_bind_groups: [[wgpu::BindGroup; 2]; 3],
+    params_buffer: wgpu::Buffer,
+    size: (u32, u32),
+    /// Splats of the coming frames, one per frame
+    splats: VecDeque<Splat>,
+    /// Whether this frame has a splat to apply
+    splatting: bool,
+    /// Hue of the next pointer splat
+    hue: f32,
+    pub params: FluidParams,
+    pub view: FluidView,
+}
+
+impl FluidSim {
+    /// Create the simulation for a preview with the given width to height
+    /// ratio, drawing into `color_format` targets
+    pub fn new(device: &wgpu::Device, color_format: wgpu::TextureFormat, aspect: f32) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        let size = grid_size(aspect);
+
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let velocity = PingPong::new(device, "Fluid Velocity", size);
+        let dye = PingPong::new(device, "Fluid Dye", size);
+        let pressure = PingPong::new(device, "Fluid Pressure", size);
+        let divergence_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Fluid Divergence"),
+            size: wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FIELD_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING,
+            view_formats: &[],
+        });
+        let divergence_view = divergence_texture.create_view(&Default::default());
+
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Fluid Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Fluid Params"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let params_entry = |visibility| wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let sampler_entry = |visibility| wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility,
+            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        };
+        let texture_entry = |binding, visibility| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let compute = wgpu::ShaderStages::COMPUTE;
+        let fragment = wgpu::ShaderStages::FRAGMENT;
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let step_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Fluid Step Bind Group Layout"),
+            entries: &[
+                params_entry(compute),
+                sampler_entry(compute),
+                texture_entry(2, compute),
+                texture_entry(3, compute),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: compute,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: FIELD_FORMAT,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let display_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Fluid Display Bind Group Layout"),
+            entries: &[
+                params_entry(fragment),
+                sampler_entry(fragment),
+                texture_entry(2, fragment),
+            ],
+        });
+
+        let step = |a: &wgpu::TextureView, b: &wgpu::TextureView, output: &wgpu::TextureView| {
+            tracker.record(ApiCategory::BindGroup, "create_bind_group");
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Fluid Step Bind Group"),
+                layout: &step_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(a),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(b),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(output),
+                    },
+                ],
+            })
+        };
+        let (vel, dyes, pres) = (&velocity.views, &dye.views, &pressure.views);
+        let velocity_steps = [0, 1].map(|v| step(&vel[v], &vel[v], &vel[1 - v]));
+        let dye_steps = [0, 1].map(|d| step(&dyes[d], &dyes[d], &dyes[1 - d]));
+        let advect_dye_steps =
+            [0, 1].map(|v| [0, 1].map(|d| step(&vel[v], &dyes[d], &dyes[1 - d])));
+        let divergence_steps = [0, 1].map(|v| step(&vel[v], &vel[v], &divergence_view));
+        let jacobi_steps = [0, 1].map(|p| step(&pres[p], &divergence_view, &pres[1 - p]));
+        let gradient_steps = [0, 1].map(|p| [0, 1].map(|v| step(&pres[p], &vel[v], &vel[1 - v])));
+        let display_bind_groups = [dyes, vel, pres].map(|views| {
+            [0, 1].map(|index| {
+                tracker.record(ApiCategory::BindGroup, "create_bind_group");
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Fluid Display Bind Group"),
+                    layout: &display_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: params_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(&sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(&views[index]),
+                        },
+                    ],
+                })
+            })
+        });
+
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let simulation_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Fluid Simulation Shader"),
+            source: wgpu::ShaderSource::Wgsl(FLUID_SIMULATION_SHADER.into()),
+        });
+        let display_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Fluid Display Shader"),
+            source: wgpu::ShaderSource::Wgsl(FLUID_DISPLAY_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let step_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Fluid Step Pipeline Layout"),
+            bind_group_layouts: &[Some(&step_layout)],
+            immediate_size: 0,
+        });
+        let display_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Fluid Display Pipeline Layout"),
+                bind_group_layouts: &[Some(&display_layout)],
+                immediate_size: 0,
+            });
+
+        let create_pipeline = |entry_point| {
+            tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&step_pipeline_layout),
+                module: &simulation_shader,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let display_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Fluid Display Pipeline"),
+            layout: Some(&display_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &display_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &display_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        let mut sim = Self {
+            splat_velocity: create_pipeline("splat_velocity"),
+            splat_dye: create_pipeline("splat_dye"),
+            advect_velocity: create_pipeline("advect_velocity"),
+            advect_dye: create_pipeline("advect_dye"),
+            divergence: create_pipeline("divergence"),
+            jacobi: create_pipeline("jacobi"),
+            subtract_gradient: create_pipeline("subtract_gradient"),
+            display_pipeline,
+            velocity,
+            dye,
+            pressure,
+            divergence_texture,
+            velocity_steps,
+            dye_steps,
+            advect_dye_steps,
+            divergence_steps,
+            jacobi_steps,
+            gradient_steps,
+            display_bind_groups,
+            params_buffer,
+            size,
+            splats: VecDeque::new(),
+            splatting: false,
+            hue: 0.0,
+            params: FluidParams::default(),
+            view: FluidView::Dye,
+        };
+        // Something to look at before the first drag
+        sim.random_splats(5);
+        sim
+    }
+
+    /// Cells of the grid, width by height
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Compute dispatches per frame with the current parameters
+    pub fn dispatches_per_frame(&self) -> u32 {
+        // Two advections, divergence, the pressure solve and the gradient
+        4 + self.params.pressure_iterations.min(MAX_PRESSURE_ITERATIONS)
+    }
+
+    /// Push the fluid at `point` by a pointer drag of `delta`, both as a
+    /// fraction of the preview from its top left, and add dye there
+    pub fn drag(&mut self, point: [f32; 2], delta: [f32; 2]) {
+        // A drag across the whole preview in one frame moves the fluid at
+        // about 20 grids per second
+        let scale = 20.0 * self.params.splat_force;
+        let force = [
+            delta[0] * self.size.0 as f32 * scale,
+            delta[1] * self.size.1 as f32 * scale,
+        ];
+        self.hue = (self.hue + 0.002).fract();
+        // A drag replaces the splats still queued, so it reacts right away
+        self.splats.clear();
+        self.splats.push_back(Splat {
+            point,
+            force,
+            color: hue_color(self.hue),
+        });
+    }
+
+    /// Queue `count` splats of random position, direction and color, one
+    /// per frame
+    pub fn random_splats(&mut self, count: u32) {
+        let mut state = (self.hue * u32::MAX as f32) as u32 | 1;
+        let mut random = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as f32 / u32::MAX as f32
+        };
+        for _ in 0..count {
+            let angle = random() * std::f32::consts::TAU;
+            let speed = 400.0 * self.params.splat_force;
+            self.hue = (self.hue + 0.17).fract();
+            self.splats.push_back(Splat {
+                point: [0.2 + random() * 0.6, 0.2 + random() * 0.6],
+                force: [angle.cos() * speed, angle.sin() * speed],
+                color: hue_color(self.hue),
+            });
+        }
+    }
+
+    /// Splats waiting for the coming frames
+    pub fn pending_splats(&self) -> usize {
+        self.splats.len()
+    }
+
+    /// Clear velocity, dye and pressure
+    pub fn reset(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.splats.clear();
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Fluid Reset Encoder"),
+        });
+        for field in [&self.velocity, &self.dye, &self.pressure] {
+            for view in &field.views {
+                tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Fluid Reset Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                    multiview_mask: None,
+                });
+            }
+        }
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Upload the parameters of the next frame, taking its splat if any
+    pub fn update(&mut self, queue: &wgpu::Queue, delta_time: f32) {
+        let splat = self.splats.pop_front();
+        self.splatting = splat.is_some();
+        let splat = splat.unwrap_or(Splat {
+            point: [0.0; 2],
+            force: [0.0; 2],
+            color: [0.0; 3],
+        });
+        let [r, g, b] = splat.color;
+        let params = Params {
+            texel: [1.0 / self.size.0 as f32, 1.0 / self.size.1 as f32],
+            delta_time,
+            dye_dissipation: self.params.dye_dissipation,
+            splat_point: splat.point,
+            splat_force: splat.force,
+            splat_color: [r, g, b, 1.0],
+            splat_radius: self.params.splat_radius,
+            velocity_dissipation: self.params.velocity_dissipation,
+            view: self.view.shader_value(),
+            _padding: 0,
+        };
+        upload_belt::write_buffer(queue, &self.params_buffer, 0, bytemuck::bytes_of(&params));
+    }
+
+    /// Step the simulation and draw the chosen field into `target`
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+    ) {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Fluid Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Fluid Simulation Pass"),
+                timestamp_writes: None,
+            });
+            let workgroups = (
+                self.size.0.div_ceil(WORKGROUP_SIZE),
+                self.size.1.div_ceil(WORKGROUP_SIZE),
+            );
+            let mut dispatch = |pipeline: &wgpu::ComputePipeline, bind_group: &wgpu::BindGroup| {
+                tracker.record(ApiCategory::ComputePass, "set_pipeline");
+                pass.set_pipeline(pipeline);
+                tracker.record(ApiCategory::ComputePass, "set_bind_group");
+                pass.set_bind_group(0, bind_group, &[]);
+                tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+                pass.dispatch_workgroups(workgroups.0, workgroups.1, 1);
+            };
+
+            if self.splatting {
+                dispatch(
+                    &self.splat_velocity,
+                    &self.velocity_steps[self.velocity.current],
+                );
+                self.velocity.swap();
+                dispatch(&self.splat_dye, &self.dye_steps[self.dye.current]);
+                self.dye.swap();
+            }
+
+            dispatch(
+                &self.advect_velocity,
+                &self.velocity_steps[self.velocity.current],
+            );
+            self.velocity.swap();
+            dispatch(
+                &self.advect_dye,
+                &self.advect_dye_steps[self.velocity.current][self.dye.current],
+            );
+            self.dye.swap();
+
+            dispatch(
+                &self.divergence,
+                &self.divergence_steps[self.velocity.current],
+            );
+            // Starting from last frame's pressure converges faster than zero
+            for _ in 0..self.params.pressure_iterations.min(MAX_PRESSURE_ITERATIONS) {
+                dispatch(&self.jacobi, &self.jacobi_steps[self.pressure.current]);
+                self.pressure.swap();
+            }
+            dispatch(
+                &self.subtract_gradient,
+                &self.gradient_steps[self.pressure.current][self.velocity.current],
+            );
+            self.velocity.swap();
+        }
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Fluid Display Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            let current = match self.view {
+                FluidView::Dye => self.dye.current,
+                FluidView::Velocity => self.velocity.current,
+                FluidView::Pressure => self.pressure.current,
+            };
+            render_pass.set_pipeline(&self.display_pipeline);
+            render_pass.set_bind_group(
+                0,
+                &self.display_bind_groups[self.view as usize][current],
+                &[],
+            );
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..3, 0..1);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+
+    /// The texture holding the latest velocity, in cells per second
+    pub fn velocity_texture(&self) -> &wgpu::Texture {
+        &self.velocity.textures[self.velocity.current]
+    }
+
+    /// The texture holding the latest dye
+    pub fn dye_texture(&self) -> &wgpu::Texture {
+        &self.dye.textures[self.dye.current]
+    }
+
+    /// The divergence of the velocity before this frame's pressure solve
+    pub fn divergence_texture(&self) -> &wgpu::Texture {
+        &self.divergence_texture
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        validate(FLUID_SIMULATION_SHADER);
+        validate(FLUID_DISPLAY_SHADER);
+    }
+
+    #[test]
+    fn test_params_layout() {
+        assert_eq!(std::mem::size_of::<Params>(), 64);
+    }
+
+    #[test]
+    fn test_view_values_match_the_shader() {
+        assert!(FLUID_DISPLAY_SHADER.contains(&format!(
+            "const VIEW_VELOCITY: u32 = {}u;",
+            FluidView::Velocity.shader_value()
+        )));
+        assert_eq!(FluidView::Dye.shader_value(), 0);
+    }
+
+    #[test]
+    fn test_hue_color() {
+        assert_eq!(hue_color(0.0), [1.0, 0.0, 0.0]);
+        assert_eq!(hue_color(1.0 / 3.0), [0.0, 1.0, 0.0]);
+        assert_eq!(hue_color(2.0 / 3.0), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_grid_size_follows_aspect() {
+        assert_eq!(grid_size(1.0), (GRID_HEIGHT, GRID_HEIGHT));
+        assert_eq!(grid_size(1.5), (GRID_HEIGHT * 3 / 2, GRID_HEIGHT));
+    }
+}
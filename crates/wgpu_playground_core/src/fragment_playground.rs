@@ -0,0 +1,648 @@
+//! ShaderToy-style playground for fullscreen fragment shaders
+//!
+//! Only a `mainImage` function is written. A fullscreen triangle calls it
+//! for every pixel of the preview with the pixel position, origin at the
+//! bottom left like ShaderToy's `fragCoord`, and it returns the color.
+//! The inputs ShaderToy shaders expect are filled in every frame:
+//!
+//! - `iResolution`: preview size in pixels, with 1 as `z`
+//! - `iTime`, `iTimeDelta`: seconds since the start and since the last frame
+//! - `iFrame`: frames drawn since the start
+//! - `iMouse`: `xy` is where the mouse is while a button is held on the
+//!   preview, `zw` where the click was; `z` turns negative once released
+//!   and `w` is only positive on the frame of the click
+//! - `iAudio`, `iBeat` and `audioBand(i)`: loudness, beat and spectrum of
+//!   the [audio input](crate::audio_input), all zero while it's off
+//!
+//! They live in a uniform buffer at `@group(0)` that is appended after the
+//! shader, so error lines match the editor. Uniforms of the shader's own
+//! go in `@group(1)` or later and get widgets like in the shader editor,
+//! and can be driven by [gamepad and MIDI controls](crate::controller_input).
+//! Pasted ShaderToy GLSL can be translated with the
+//! [shim](crate::shadertoy_shim), and the [SDF gallery](crate::sdf_gallery)
+//! has ray marched scenes to start from.
+
+use crate::audio_input::AudioInput;
+use crate::controller_input::{self, ControllerInput};
+use crate::device_recovery::ResourceRecreate;
+use crate::pixel_picker::PixelPicker;
+use crate::sdf_gallery::{self, SdfExample, SdfParamKind};
+use crate::shader_preview::ShaderPreview;
+use crate::shadertoy_shim::{self, ShimHint};
+use crate::uniform_tweaker::UniformTweaker;
+
+/// Inputs, fullscreen triangle and entry points appended to the shader
+pub const PRELUDE: &str = r#"
+// Playground inputs, appended after the shader
+
+struct PlaygroundInputs {
+    iResolution: vec3<f32>,
+    iTime: f32,
+    iMouse: vec4<f32>,
+    iTimeDelta: f32,
+    iFrame: i32,
+    iBeat: f32,
+    iAudio: vec4<f32>,
+    iSpectrum: array<vec4<f32>, 4>,
+}
+
+@group(0) @binding(0) var<uniform> playground: PlaygroundInputs;
+
+var<private> iResolution: vec3<f32>;
+var<private> iTime: f32;
+var<private> iTimeDelta: f32;
+var<private> iFrame: i32;
+var<private> iMouse: vec4<f32>;
+// Level, bass, mid and treble of the audio input, 0..1
+var<private> iAudio: vec4<f32>;
+// 1 on a beat, decaying towards 0
+var<private> iBeat: f32;
+
+// Loudness of spectrum band `i` of 16, from low to high frequencies
+fn audioBand(i: i32) -> f32 {
+    let band = clamp(i, 0, 15);
+    return playground.iSpectrum[band / 4][band % 4];
+}
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_playground(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    iResolution = playground.iResolution;
+    iTime = playground.iTime;
+    iTimeDelta = playground.iTimeDelta;
+    iFrame = playground.iFrame;
+    iMouse = playground.iMouse;
+    iAudio = playground.iAudio;
+    iBeat = playground.iBeat;
+    return mainImage(vec2<f32>(position.x, iResolution.y - position.y));
+}
+"#;
+
+/// The shader a new playground starts with
+pub const DEFAULT_SOURCE: &str = r#"// Called for every pixel; fragCoord is in pixels from the bottom left
+fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {
+    let uv = fragCoord / iResolution.xy;
+    var col = 0.5 + 0.5 * cos(iTime + uv.xyx + vec3<f32>(0.0, 2.0, 4.0));
+
+    // Hold a mouse button on the preview to draw a ring
+    if (iMouse.z > 0.0) {
+        let ring = abs(length(fragCoord - iMouse.xy) - 20.0);
+        col = mix(vec3<f32>(1.0), col, smoothstep(1.0, 3.0, ring));
+    }
+    return vec4<f32>(col, 1.0);
+}
+"#;
+
+/// The shader followed by the [`PRELUDE`]
+pub fn compose(source: &str) -> String {
+    format!("{}\n{}", source, PRELUDE)
+}
+
+/// ShaderToy's `iMouse`, updated once per frame
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MouseInput {
+    value: [f32; 4],
+    down: bool,
+}
+
+impl MouseInput {
+    /// Advance a frame; `pressed_at` is the pixel position while a button
+    /// is held on the preview
+    pub fn update(&mut self, pressed_at: Option<[f32; 2]>) {
+        let [_, _, click_x, click_y] = self.value;
+        match pressed_at {
+            Some([x, y]) if self.down => self.value = [x, y, click_x, -click_y.abs()],
+            Some([x, y]) => self.value = [x, y, x, y],
+            None => self.value = [self.value[0], self.value[1], -click_x.abs(), -click_y.abs()],
+        }
+        self.down = pressed_at.is_some();
+    }
+
+    pub fn value(&self) -> [f32; 4] {
+        self.value
+    }
+}
+
+/// Fullscreen fragment shader editor with a live preview
+pub struct FragmentPlayground {
+    source: String,
+    /// Source the preview was last built from
+    built_source: Option<String>,
+    uniforms: UniformTweaker,
+    /// Created on first use
+    preview: Option<ShaderPreview>,
+    /// Shift-hover readback of the preview texels
+    pixel_picker: PixelPicker,
+    /// Hints from the last GLSL translation
+    hints: Vec<ShimHint>,
+    /// GLSL pasted for translation
+    glsl_input: String,
+    /// Gallery entry the source was loaded from
+    gallery_example: Option<&'static SdfExample>,
+    /// Set the entry's parameters once its source is reflected
+    apply_gallery_defaults: bool,
+    /// Stop `iTime` and `iFrame`
+    pub paused: bool,
+    time: f32,
+    time_delta: f32,
+    frame: i32,
+    mouse: MouseInput,
+    pub audio: AudioInput,
+    /// Mappings from gamepad and MIDI controls to the shader's uniforms
+    pub controllers: ControllerInput,
+}
+
+impl Default for FragmentPlayground {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FragmentPlayground {
+    pub fn new() -> Self {
+        Self {
+            source: DEFAULT_SOURCE.to_string(),
+            built_source: None,
+            uniforms: UniformTweaker::new(),
+            preview: None,
+            pixel_picker: PixelPicker::new(),
+            hints: Vec::new(),
+            glsl_input: shadertoy_shim::SHADERTOY_TEMPLATE.to_string(),
+            gallery_example: None,
+            apply_gallery_defaults: false,
+            paused: false,
+            time: 0.0,
+            time_delta: 0.0,
+            frame: 0,
+            mouse: MouseInput::default(),
+            audio: AudioInput::new(),
+            controllers: ControllerInput::new(),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = source.into();
+    }
+
+    /// Replace the shader with GLSL translated to WGSL
+    ///
+    /// Returns the hints for what the translation left to do by hand.
+    pub fn load_glsl(&mut self, glsl: &str) -> &[ShimHint] {
+        let translation = shadertoy_shim::translate(glsl);
+        self.source = translation.source;
+        self.hints = translation.hints;
+        self.gallery_example = None;
+        &self.hints
+    }
+
+    /// Replace the shader with an entry of the SDF gallery, its parameters
+    /// at their starting values
+    pub fn load_gallery_example(&mut self, example: &'static SdfExample) {
+        self.source = example.source();
+        self.hints.clear();
+        self.gallery_example = Some(example);
+        self.apply_gallery_defaults = true;
+        // Reloading the same entry resets its parameters too
+        self.built_source = None;
+    }
+
+    /// Gallery entry the shader was loaded from, if any
+    pub fn gallery_example(&self) -> Option<&'static SdfExample> {
+        self.gallery_example
+    }
+
+    /// Value of a uniform of the shader's, see [`UniformTweaker::get`]
+    pub fn uniform(&self, name: &str) -> Option<Vec<f32>> {
+        self.uniforms.get(name)
+    }
+
+    /// Why the shader can't be drawn
+    pub fn error(&self) -> Option<&str> {
+        self.preview.as_ref().and_then(|preview| preview.error())
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn frame(&self) -> i32 {
+        self.frame
+    }
+
+    /// Start `iTime` and `iFrame` from zero again
+    pub fn restart(&mut self) {
+        self.time = 0.0;
+        self.time_delta = 0.0;
+        self.frame = 0;
+    }
+
+    /// Advance the clock by `dt` seconds unless paused, analyze the audio
+    /// of that time and read the controllers
+    pub fn advance(&mut self, dt: f32) {
+        self.time_delta = if self.paused { 0.0 } else { dt };
+        self.time += self.time_delta;
+        self.audio.update(dt);
+        self.controllers.update(dt);
+    }
+
+    /// Feed the mouse for this frame, see [`MouseInput::update`]
+    pub fn update_mouse(&mut self, pressed_at: Option<[f32; 2]>) {
+        self.mouse.update(pressed_at);
+    }
+
+    /// Build the preview if the source changed since the last build
+    fn refresh(&mut self, device: &wgpu::Device) {
+        let preview = self
+            .preview
+            .get_or_insert_with(|| ShaderPreview::new(device));
+        if self.built_source.as_ref() == Some(&self.source) {
+            return;
+        }
+        self.built_source = Some(self.source.clone());
+        let source = compose(&self.source);
+        match naga::front::wgsl::parse_str(&source) {
+            Ok(module) => {
+                if let Err(e) = self.uniforms.reflect(&module) {
+                    log::warn!("Failed to reflect uniforms: {}", e);
+                }
+                if let Some(example) = self.gallery_example.filter(|_| self.apply_gallery_defaults)
+                {
+                    for param in example.params {
+                        self.uniforms.set(param.name, &[param.default]);
+                    }
+                    self.apply_gallery_defaults = false;
+                }
+                preview.build(device, &source, &self.uniforms);
+            }
+            Err(e) => preview.set_error(e.emit_to_string(&source)),
+        }
+    }
+
+    /// Draw a frame of `width`x`height` pixels
+    pub fn render(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, width: u32, height: u32) {
+        self.refresh(device);
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+        preview.resize(device, width, height);
+        let (width, height) = preview.size();
+        self.uniforms
+            .set("iResolution", &[width as f32, height as f32, 1.0]);
+        self.uniforms.set("iTime", &[self.time]);
+        self.uniforms.set("iTimeDelta", &[self.time_delta]);
+        self.uniforms.set("iFrame", &[self.frame as f32]);
+        self.uniforms.set("iMouse", &self.mouse.value());
+        let audio = self.audio.features();
+        self.uniforms.set("iAudio", &audio.summary());
+        self.uniforms.set("iBeat", &[audio.beat]);
+        for (i, bands) in audio.bands.chunks(4).enumerate() {
+            self.uniforms.set(&format!("iSpectrum[{}]", i), bands);
+        }
+        self.controllers.mapper.apply(&mut self.uniforms);
+        preview.render(device, queue, &self.uniforms);
+        if !self.paused {
+            self.frame += 1;
+        }
+    }
+
+    /// Texture the frames are drawn into
+    pub fn texture(&self) -> Option<&wgpu::Texture> {
+        self.preview.as_ref().map(|preview| preview.texture())
+    }
+
+    /// Render the editor without the preview
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🖼 Fragment Playground");
+        ui.label(
+            "Write a mainImage function and it runs for every pixel of a fullscreen triangle, \
+            with iResolution, iTime, iTimeDelta, iFrame and iMouse filled in like on ShaderToy.",
+        );
+        ui.add_space(5.0);
+        self.editor_ui(ui);
+    }
+
+    /// Render the editor next to the live preview (Native version)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ui_with_preview(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        ui.heading("🖼 Fragment Playground");
+        ui.add_space(5.0);
+
+        // The audio panel sits next to the preview
+        let side_width = 220.0;
+        let spacing = ui.spacing().item_spacing.x;
+        let width = (ui.available_width() - side_width - spacing).clamp(160.0, 960.0);
+        let size = egui::vec2(width, (width * 9.0 / 16.0).round());
+        let pixels = size * ui.ctx().pixels_per_point();
+        self.advance(ui.input(|i| i.stable_dt));
+        self.render(device, queue, pixels.x as u32, pixels.y as u32);
+        ui.horizontal_top(|ui| {
+            if let Some(preview) = &mut self.preview {
+                let texture_id = preview.texture_id(device, renderer);
+                let image = egui::Image::new(egui::load::SizedTexture::new(texture_id, size))
+                    .sense(egui::Sense::click_and_drag());
+                let response = ui.add(image);
+                // ShaderToy's mouse is in pixels from the bottom left
+                let pressed_at = response
+                    .interact_pointer_pos()
+                    .filter(|_| response.is_pointer_button_down_on())
+                    .map(|pos| {
+                        let (width, height) = preview.size();
+                        let at = pos - response.rect.min;
+                        [
+                            at.x / response.rect.width() * width as f32,
+                            (1.0 - at.y / response.rect.height()) * height as f32,
+                        ]
+                    });
+                self.pixel_picker
+                    .image_hover_ui(ui, &response, device, queue, preview.texture());
+                self.update_mouse(pressed_at);
+            }
+            ui.vertical(|ui| {
+                ui.set_width(side_width);
+                self.audio.ui(ui);
+            });
+        });
+
+        ui.horizontal(|ui| {
+            let label = if self.paused { "▶ Play" } else { "⏸ Pause" };
+            if ui.button(label).clicked() {
+                self.paused = !self.paused;
+            }
+            if ui.button("⏮ Restart").clicked() {
+                self.restart();
+            }
+            let [x, y, z, w] = self.mouse.value();
+            ui.label(format!(
+                "iTime {:.2}   iFrame {}   iMouse ({:.0}, {:.0}, {:.0}, {:.0})",
+                self.time, self.frame, x, y, z, w
+            ));
+        });
+        if let Some(error) = self.error() {
+            ui.colored_label(
+                egui::Color32::from_rgb(220, 50, 50),
+                format!("❌ {}", error),
+            );
+        }
+
+        ui.add_space(5.0);
+        self.editor_ui(ui);
+
+        ui.add_space(10.0);
+        egui::CollapsingHeader::new("🎚 Uniforms")
+            .id_salt("fragment_playground_uniforms")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.label(
+                    "Uniforms at @group(1) or later get a widget; @group(0) holds the inputs.",
+                );
+                self.uniforms.ui_filtered(ui, |block| block.group != 0);
+            });
+
+        ui.add_space(5.0);
+        egui::CollapsingHeader::new("🎮 Controllers")
+            .id_salt("fragment_playground_controllers")
+            .show(ui, |ui| {
+                ui.label(
+                    "Bind gamepad axes and buttons or MIDI knobs to the uniforms. A mapping \
+                    takes over its uniform once its control moves.",
+                );
+                let targets =
+                    controller_input::uniform_targets(&self.uniforms, |block| block.group != 0);
+                self.controllers.ui(ui, &targets);
+            });
+        ui.ctx().request_repaint();
+    }
+
+    /// Gallery picker and the parameters of the loaded entry
+    fn gallery_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("🔭 SDF Gallery:");
+            let selected = self
+                .gallery_example
+                .map_or("Choose a scene...", |example| example.name);
+            let mut chosen = self.gallery_example;
+            egui::ComboBox::from_id_salt("fragment_playground_sdf_gallery")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for example in sdf_gallery::SDF_GALLERY {
+                        ui.selectable_value(&mut chosen, Some(example), example.name);
+                    }
+                });
+            if let Some(example) = chosen.filter(|_| chosen != self.gallery_example) {
+                self.load_gallery_example(example);
+            }
+        });
+        let Some(example) = self.gallery_example else {
+            return;
+        };
+        ui.colored_label(egui::Color32::GRAY, example.description);
+        egui::Grid::new("fragment_playground_sdf_params")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                for param in example.params {
+                    // Fields edited away in the source have no value
+                    let Some(value) = self.uniforms.get(param.name) else {
+                        continue;
+                    };
+                    let mut value = value[0];
+                    ui.label(param.label);
+                    let changed = match param.kind {
+                        SdfParamKind::Range(min, max) => {
+                            ui.add(egui::Slider::new(&mut value, min..=max)).changed()
+                        }
+                        SdfParamKind::Toggle => {
+                            let mut on = value != 0.0;
+                            let changed = ui.checkbox(&mut on, "").changed();
+                            value = if on { 1.0 } else { 0.0 };
+                            changed
+                        }
+                    };
+                    if changed {
+                        self.uniforms.set(param.name, &[value]);
+                    }
+                    ui.end_row();
+                }
+            });
+        if ui.button("Reset parameters").clicked() {
+            for param in example.params {
+                self.uniforms.set(param.name, &[param.default]);
+            }
+        }
+    }
+
+    /// The source editor, the GLSL translation and its hints
+    fn editor_ui(&mut self, ui: &mut egui::Ui) {
+        self.gallery_ui(ui);
+        ui.add_space(5.0);
+        ui.add(
+            egui::TextEdit::multiline(&mut self.source)
+                .font(egui::TextStyle::Monospace)
+                .code_editor()
+                .desired_rows(16)
+                .desired_width(f32::INFINITY),
+        );
+        ui.horizontal(|ui| {
+            if ui.button("Reset to default").clicked() {
+                self.source = DEFAULT_SOURCE.to_string();
+                self.hints.clear();
+                self.gallery_example = None;
+            }
+        });
+
+        ui.add_space(5.0);
+        egui::CollapsingHeader::new("🔁 Paste ShaderToy GLSL")
+            .id_salt("fragment_playground_glsl")
+            .show(ui, |ui| {
+                ui.label(
+                    "GLSL is translated on a best effort basis. What can't be rewritten is \
+                    left in place with a hint on its GLSL line.",
+                );
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.glsl_input)
+                        .font(egui::TextStyle::Monospace)
+                        .code_editor()
+                        .desired_rows(8)
+                        .desired_width(f32::INFINITY),
+                );
+                let looks_like_glsl = shadertoy_shim::looks_like_glsl(&self.glsl_input);
+                let translate =
+                    ui.add_enabled(looks_like_glsl, egui::Button::new("Translate to WGSL"));
+                if translate.clicked() {
+                    let glsl = self.glsl_input.clone();
+                    self.load_glsl(&glsl);
+                }
+                if !looks_like_glsl {
+                    ui.label("This doesn't look like GLSL.");
+                }
+            });
+        for hint in &self.hints {
+            ui.colored_label(
+                egui::Color32::from_rgb(230, 180, 60),
+                format!("💡 GLSL line {}: {}", hint.line, hint.message),
+            );
+        }
+    }
+}
+
+impl ResourceRecreate for FragmentPlayground {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // The preview is built again on the new device on the next frame
+        self.preview = None;
+        self.built_source = None;
+        self.pixel_picker.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_input::{AudioSource, BAND_COUNT};
+    use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+    fn validate(source: &str) -> Result<(), String> {
+        let composed = compose(source);
+        let module =
+            naga::front::wgsl::parse_str(&composed).map_err(|e| e.emit_to_string(&composed))?;
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .map(|_| ())
+            .map_err(|e| e.emit_to_string(&composed))
+    }
+
+    #[test]
+    fn test_default_source_validates() {
+        validate(DEFAULT_SOURCE).unwrap();
+    }
+
+    #[test]
+    fn test_translated_template_validates() {
+        let mut playground = FragmentPlayground::new();
+        assert!(playground
+            .load_glsl(shadertoy_shim::SHADERTOY_TEMPLATE)
+            .is_empty());
+        validate(playground.source()).unwrap();
+    }
+
+    #[test]
+    fn test_errors_point_at_the_shader_lines() {
+        let error =
+            validate("fn mainImage(p: vec2<f32>) -> vec4<f32> {\n    return nope;\n}").unwrap_err();
+        assert!(error.contains(":2:"), "{error}");
+    }
+
+    #[test]
+    fn test_inputs_layout() {
+        let tweaker = UniformTweaker::from_wgsl(&compose(DEFAULT_SOURCE)).unwrap();
+        let block = tweaker.block(0, 0).unwrap();
+        let offset = |name| block.field(name).unwrap().offset;
+        assert_eq!(offset("iResolution"), 0);
+        assert_eq!(offset("iTime"), 12);
+        assert_eq!(offset("iMouse"), 16);
+        assert_eq!(offset("iTimeDelta"), 32);
+        assert_eq!(offset("iFrame"), 36);
+        assert_eq!(offset("iBeat"), 40);
+        assert_eq!(offset("iAudio"), 48);
+        assert_eq!(offset("iSpectrum[3]"), 112);
+        assert_eq!(block.size(), 128);
+        // `audioBand` reads four bands from every vector
+        assert_eq!(BAND_COUNT, 16);
+    }
+
+    #[test]
+    fn test_mouse_follows_shadertoy() {
+        let mut mouse = MouseInput::default();
+        mouse.update(Some([10.0, 20.0]));
+        assert_eq!(mouse.value(), [10.0, 20.0, 10.0, 20.0]);
+        mouse.update(Some([15.0, 25.0]));
+        assert_eq!(mouse.value(), [15.0, 25.0, 10.0, -20.0]);
+        mouse.update(None);
+        assert_eq!(mouse.value(), [15.0, 25.0, -10.0, -20.0]);
+        mouse.update(None);
+        assert_eq!(mouse.value(), [15.0, 25.0, -10.0, -20.0]);
+    }
+
+    #[test]
+    fn test_clock() {
+        let mut playground = FragmentPlayground::new();
+        playground.advance(0.5);
+        playground.advance(0.25);
+        assert_eq!(playground.time(), 0.75);
+        playground.paused = true;
+        playground.advance(0.5);
+        assert_eq!(playground.time(), 0.75);
+        playground.restart();
+        assert_eq!(playground.time(), 0.0);
+        assert_eq!(playground.frame(), 0);
+    }
+
+    #[test]
+    fn test_audio_runs_while_paused() {
+        let mut playground = FragmentPlayground::new();
+        playground
+            .audio
+            .set_source(AudioSource::TestSignal)
+            .unwrap();
+        playground.paused = true;
+        for _ in 0..30 {
+            playground.advance(1.0 / 60.0);
+        }
+        assert!(playground.audio.features().bass > 0.0);
+        assert_eq!(playground.time(), 0.0);
+    }
+}
@@ -0,0 +1,103 @@
+//! Screenshots of the presented window frame
+//!
+//! The GUI captures its surface texture through
+//! [`capture_texture`](crate::visual_regression::capture_texture), so every
+//! format supported there (including BGRA swapchains) works here as well.
+//! Captured frames are saved as timestamped PNG files and can be handed to
+//! egui for copying to the OS clipboard.
+
+use crate::visual_regression::{capture_texture, VisualRegressionError};
+use chrono::{DateTime, Local};
+use image::RgbaImage;
+use std::path::{Path, PathBuf};
+
+/// Default directory for saved screenshots, relative to the working directory
+pub const DEFAULT_SCREENSHOT_DIR: &str = "screenshots";
+
+/// Result of capturing a frame
+pub type CaptureResult = Result<RgbaImage, VisualRegressionError>;
+
+/// Capture the contents of a frame texture
+///
+/// The texture (usually the current surface texture) must have `COPY_SRC`
+/// usage. Blocks until the copy has finished. The alpha channel is forced to
+/// opaque since the window is presented without transparency.
+pub fn capture_frame(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> CaptureResult {
+    crate::cpu_scope!("Read back frame");
+    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+        return Err(VisualRegressionError::CaptureError(
+            "The surface does not support COPY_SRC, screenshots are unavailable".into(),
+        ));
+    }
+    let mut image = pollster::block_on(capture_texture(device, queue, texture))?;
+    for pixel in image.pixels_mut() {
+        pixel[3] = 255;
+    }
+    Ok(image)
+}
+
+/// Path of the screenshot file for a capture taken at `time`
+pub fn screenshot_path(dir: &Path, time: DateTime<Local>) -> PathBuf {
+    dir.join(format!(
+        "wgpu_playground_{}.png",
+        time.format("%Y%m%d_%H%M%S_%3f")
+    ))
+}
+
+/// Save a captured frame as PNG into `dir`, creating the directory if needed
+///
+/// Returns the path of the written file.
+pub fn save_frame(image: &RgbaImage, dir: &Path) -> Result<PathBuf, VisualRegressionError> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        VisualRegressionError::SaveError(format!("Failed to create {}: {}", dir.display(), e))
+    })?;
+    let path = screenshot_path(dir, Local::now());
+    image.save(&path).map_err(|e| {
+        VisualRegressionError::SaveError(format!("Failed to write {}: {}", path.display(), e))
+    })?;
+    Ok(path)
+}
+
+/// Convert a captured frame into an egui image, e.g. for `Context::copy_image`
+pub fn to_color_image(image: &RgbaImage) -> egui::ColorImage {
+    egui::ColorImage::from_rgba_unmultiplied(
+        [image.width() as usize, image.height() as usize],
+        image.as_raw(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_screenshot_path() {
+        let time = Local.with_ymd_and_hms(2024, 3, 7, 9, 5, 2).unwrap();
+        let path = screenshot_path(Path::new("shots"), time);
+        assert_eq!(
+            path,
+            Path::new("shots").join("wgpu_playground_20240307_090502_000.png")
+        );
+    }
+
+    #[test]
+    fn test_save_frame_and_color_image() {
+        let dir = std::env::temp_dir().join("wgpu_playground_frame_capture_test");
+        let image = RgbaImage::from_pixel(3, 2, image::Rgba([10, 20, 30, 255]));
+
+        let path = save_frame(&image, &dir).unwrap();
+        assert!(path.exists());
+        assert_eq!(image::open(&path).unwrap().to_rgba8(), image);
+
+        let color_image = to_color_image(&image);
+        assert_eq!(color_image.size, [3, 2]);
+        assert_eq!(color_image.pixels[0], egui::Color32::from_rgb(10, 20, 30));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
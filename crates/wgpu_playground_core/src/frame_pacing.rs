@@ -0,0 +1,510 @@
+//! Frame pacing measurements for the present mode experiments
+//!
+//! [`FrameTimer`] is driven by the window's render loop: it notes when a
+//! frame starts, how long acquiring the surface texture blocked, when the
+//! frame's work was submitted and presented, and, through
+//! [`wgpu::Queue::on_submitted_work_done`], when the GPU finished it. With
+//! `Fifo` the acquire is where the CPU waits for vsync; with `Mailbox` and
+//! `Immediate` it returns at once and the frame rate follows the CPU and
+//! GPU cost, which [`burn_cpu`] and [`GpuLoad`] inflate artificially.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+
+/// Number of frames kept in the history
+pub const HISTORY_FRAMES: usize = 300;
+
+/// Largest `desired_maximum_frame_latency` offered; wgpu clamps to what the
+/// platform supports
+pub const MAX_FRAME_LATENCY: u32 = 4;
+
+/// Present modes that can be selected explicitly, in the order they are shown
+pub const PRESENT_MODES: [wgpu::PresentMode; 4] = [
+    wgpu::PresentMode::Fifo,
+    wgpu::PresentMode::FifoRelaxed,
+    wgpu::PresentMode::Mailbox,
+    wgpu::PresentMode::Immediate,
+];
+
+/// What a present mode does, for the UI
+pub fn present_mode_description(mode: wgpu::PresentMode) -> &'static str {
+    match mode {
+        wgpu::PresentMode::Fifo => {
+            "Frames queue up and are shown one per vblank. No tearing; the CPU blocks in get_current_texture once the queue is full."
+        }
+        wgpu::PresentMode::FifoRelaxed => {
+            "Like Fifo, but a frame that misses its vblank is shown immediately, tearing instead of waiting for the next one."
+        }
+        wgpu::PresentMode::Mailbox => {
+            "Frames render as fast as possible; each vblank shows the newest and older ones are dropped. No tearing, low latency."
+        }
+        wgpu::PresentMode::Immediate => {
+            "Frames are shown as soon as they are presented, without waiting for vblank. Lowest latency, may tear."
+        }
+        wgpu::PresentMode::AutoVsync | wgpu::PresentMode::AutoNoVsync => {
+            "Picks a supported mode automatically."
+        }
+    }
+}
+
+/// Present mode and frame latency of the window surface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentSettings {
+    pub present_mode: wgpu::PresentMode,
+    pub desired_maximum_frame_latency: u32,
+}
+
+impl Default for PresentSettings {
+    fn default() -> Self {
+        Self {
+            present_mode: wgpu::PresentMode::Fifo,
+            desired_maximum_frame_latency: 2,
+        }
+    }
+}
+
+impl PresentSettings {
+    /// The settings a surface is configured with
+    pub fn from_config(config: &wgpu::SurfaceConfiguration) -> Self {
+        Self {
+            present_mode: config.present_mode,
+            desired_maximum_frame_latency: config.desired_maximum_frame_latency,
+        }
+    }
+
+    /// Copy the settings into a surface configuration
+    ///
+    /// A present mode missing from `supported` is replaced by `Fifo`, which
+    /// every surface supports. Returns false if that happened.
+    pub fn apply_to(
+        &self,
+        config: &mut wgpu::SurfaceConfiguration,
+        supported: &[wgpu::PresentMode],
+    ) -> bool {
+        let is_supported = supported.contains(&self.present_mode);
+        config.present_mode = if is_supported {
+            self.present_mode
+        } else {
+            wgpu::PresentMode::Fifo
+        };
+        config.desired_maximum_frame_latency = self
+            .desired_maximum_frame_latency
+            .clamp(1, MAX_FRAME_LATENCY);
+        is_supported
+    }
+}
+
+/// Timings of one presented frame
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FrameSample {
+    pub frame: u64,
+    /// Time since the previous frame started
+    pub interval: Duration,
+    /// Time blocked in `get_current_texture`
+    pub acquire: Duration,
+    /// Time from frame start to `queue.submit`, including the acquire
+    pub cpu: Duration,
+    /// Time spent in `present`
+    pub present: Duration,
+    /// Time from frame start until the GPU finished the frame's work
+    pub latency: Option<Duration>,
+}
+
+/// Averages over the frame history
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameStats {
+    pub frames: usize,
+    pub mean_interval: Duration,
+    pub max_interval: Duration,
+    pub mean_acquire: Duration,
+    pub mean_cpu: Duration,
+    pub mean_latency: Option<Duration>,
+}
+
+impl FrameStats {
+    pub fn from_samples(samples: &[FrameSample]) -> Self {
+        // The first frame has no predecessor to measure its interval against
+        let timed: Vec<&FrameSample> = samples.iter().filter(|s| s.frame > 0).collect();
+        if timed.is_empty() {
+            return Self::default();
+        }
+        let count = timed.len() as u32;
+        let latencies: Vec<Duration> = timed.iter().filter_map(|s| s.latency).collect();
+        Self {
+            frames: timed.len(),
+            mean_interval: timed.iter().map(|s| s.interval).sum::<Duration>() / count,
+            max_interval: timed.iter().map(|s| s.interval).max().unwrap_or_default(),
+            mean_acquire: timed.iter().map(|s| s.acquire).sum::<Duration>() / count,
+            mean_cpu: timed.iter().map(|s| s.cpu).sum::<Duration>() / count,
+            mean_latency: (!latencies.is_empty())
+                .then(|| latencies.iter().sum::<Duration>() / latencies.len() as u32),
+        }
+    }
+
+    /// Frames per second from the mean interval
+    pub fn fps(&self) -> f64 {
+        if self.mean_interval.is_zero() {
+            0.0
+        } else {
+            1.0 / self.mean_interval.as_secs_f64()
+        }
+    }
+}
+
+/// Records when each stage of a frame happened
+///
+/// Call [`Self::begin_frame`], [`Self::acquired`], [`Self::submitted`] and
+/// [`Self::presented`] in that order from the render loop. A frame that is
+/// not submitted is left out of the history.
+#[derive(Default)]
+pub struct FrameTimer {
+    /// Shared with the work-done callbacks, which fill in the latency
+    samples: Arc<Mutex<VecDeque<FrameSample>>>,
+    frame: u64,
+    frame_start: Option<Instant>,
+    previous_start: Option<Instant>,
+    stage_start: Option<Instant>,
+    current: FrameSample,
+}
+
+impl FrameTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn begin_frame(&mut self) {
+        let now = Instant::now();
+        self.current = FrameSample {
+            frame: self.frame,
+            interval: self
+                .previous_start
+                .map(|previous| now - previous)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        self.frame_start = Some(now);
+        self.stage_start = Some(now);
+    }
+
+    /// The surface texture was acquired
+    pub fn acquired(&mut self) {
+        if let Some(start) = self.stage_start {
+            self.current.acquire = start.elapsed();
+        }
+    }
+
+    /// The frame's work was submitted; the latency arrives when the GPU
+    /// has finished it
+    pub fn submitted(&mut self, queue: &wgpu::Queue) {
+        let Some(frame_start) = self.record_submission() else {
+            return;
+        };
+        let frame = self.current.frame;
+        let samples = self.samples.clone();
+        queue.on_submitted_work_done(move || {
+            let latency = frame_start.elapsed();
+            let mut samples = samples.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(sample) = samples.iter_mut().rev().find(|s| s.frame == frame) {
+                sample.latency = Some(latency);
+            }
+        });
+    }
+
+    /// Add the current frame to the history
+    ///
+    /// This happens at submit rather than present so the work-done callback
+    /// finds the sample even if the device is polled before presenting.
+    /// Returns the frame's start time.
+    fn record_submission(&mut self) -> Option<Instant> {
+        let frame_start = self.frame_start?;
+        self.current.cpu = frame_start.elapsed();
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        if samples.len() == HISTORY_FRAMES {
+            samples.pop_front();
+        }
+        samples.push_back(self.current.clone());
+        drop(samples);
+        self.stage_start = Some(Instant::now());
+        Some(frame_start)
+    }
+
+    /// The frame was presented; completes its sample
+    pub fn presented(&mut self) {
+        let Some(frame_start) = self.frame_start.take() else {
+            return;
+        };
+        let present = self
+            .stage_start
+            .take()
+            .map(|start| start.elapsed())
+            .unwrap_or_default();
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(sample) = samples
+            .iter_mut()
+            .rev()
+            .find(|s| s.frame == self.current.frame)
+        {
+            sample.present = present;
+        }
+        drop(samples);
+
+        self.previous_start = Some(frame_start);
+        self.frame += 1;
+    }
+
+    /// Skip the current frame, e.g. when no surface texture was available
+    pub fn cancel_frame(&mut self) {
+        self.frame_start = None;
+        self.stage_start = None;
+    }
+
+    /// The recorded frames, oldest first
+    ///
+    /// The newest frames may not have their latency yet.
+    pub fn samples(&self) -> Vec<FrameSample> {
+        self.samples
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Forget the history, e.g. after the present mode changed
+    pub fn clear(&mut self) {
+        self.samples = Arc::new(Mutex::new(VecDeque::new()));
+        self.previous_start = None;
+        // A frame in progress is restarted as the first one
+        self.frame = 0;
+        self.current.frame = 0;
+    }
+}
+
+/// Spin for `duration` to simulate CPU work in a frame
+///
+/// Sleeping would give the time slice back to the OS and oversleep; a
+/// frame's CPU cost is busy time.
+pub fn burn_cpu(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
+
+const GPU_LOAD_SHADER: &str = r#"
+@group(0) @binding(0) var<uniform> iterations: u32;
+@group(0) @binding(1) var<storage, read_write> output: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    var x = id.x;
+    for (var i = 0u; i < iterations; i++) {
+        x = x * 1664525u + 1013904223u;
+        x ^= x >> 16u;
+    }
+    output[id.x] = x;
+}
+"#;
+
+/// Workgroups of 64 invocations dispatched by [`GpuLoad`]
+const GPU_LOAD_WORKGROUPS: u32 = 256;
+
+/// A busy-loop compute dispatch that adds GPU cost to a frame
+pub struct GpuLoad {
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    iterations_buffer: wgpu::Buffer,
+}
+
+impl GpuLoad {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("GPU Load Shader"),
+            source: wgpu::ShaderSource::Wgsl(GPU_LOAD_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("GPU Load Pipeline"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let iterations_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Load Iterations"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Load Output"),
+            size: (GPU_LOAD_WORKGROUPS * 64) as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("GPU Load Bind Group"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: iterations_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            pipeline,
+            bind_group,
+            iterations_buffer,
+        }
+    }
+
+    /// Submit one dispatch running `iterations` loop iterations per
+    /// invocation
+    pub fn submit(&self, device: &wgpu::Device, queue: &wgpu::Queue, iterations: u32) {
+        queue.write_buffer(
+            &self.iterations_buffer,
+            0,
+            bytemuck::cast_slice(&[iterations, 0, 0, 0]),
+        );
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("GPU Load Encoder"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("GPU Load Pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.dispatch_workgroups(GPU_LOAD_WORKGROUPS, 1, 1);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> wgpu::SurfaceConfiguration {
+        wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            width: 64,
+            height: 64,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        }
+    }
+
+    #[test]
+    fn test_apply_to_falls_back_to_fifo() {
+        let mut config = config();
+        let settings = PresentSettings {
+            present_mode: wgpu::PresentMode::Mailbox,
+            desired_maximum_frame_latency: 9,
+        };
+        assert!(settings.apply_to(
+            &mut config,
+            &[wgpu::PresentMode::Fifo, wgpu::PresentMode::Mailbox]
+        ));
+        assert_eq!(config.present_mode, wgpu::PresentMode::Mailbox);
+        assert_eq!(config.desired_maximum_frame_latency, MAX_FRAME_LATENCY);
+
+        assert!(!settings.apply_to(&mut config, &[wgpu::PresentMode::Fifo]));
+        assert_eq!(config.present_mode, wgpu::PresentMode::Fifo);
+        assert_eq!(
+            PresentSettings::from_config(&config).present_mode,
+            wgpu::PresentMode::Fifo
+        );
+    }
+
+    #[test]
+    fn test_timer_records_frames() {
+        let mut timer = FrameTimer::new();
+        for _ in 0..3 {
+            timer.begin_frame();
+            timer.acquired();
+            timer.record_submission();
+            timer.presented();
+        }
+        timer.begin_frame();
+        timer.cancel_frame();
+        timer.presented();
+        // Frames that are presented without a submit are not recorded
+        timer.begin_frame();
+        timer.presented();
+
+        let samples = timer.samples();
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[0].interval, Duration::ZERO);
+        assert_eq!(samples[2].frame, 2);
+        assert!(samples[1].interval > Duration::ZERO);
+
+        timer.clear();
+        assert!(timer.samples().is_empty());
+    }
+
+    #[test]
+    fn test_frame_stats() {
+        let sample = |frame, interval_ms, latency_ms: Option<u64>| FrameSample {
+            frame,
+            interval: Duration::from_millis(interval_ms),
+            acquire: Duration::from_millis(interval_ms / 2),
+            cpu: Duration::from_millis(interval_ms),
+            present: Duration::ZERO,
+            latency: latency_ms.map(Duration::from_millis),
+        };
+        let samples = vec![
+            sample(0, 0, Some(40)),
+            sample(1, 10, Some(20)),
+            sample(2, 30, None),
+        ];
+        let stats = FrameStats::from_samples(&samples);
+        assert_eq!(stats.frames, 2);
+        assert_eq!(stats.mean_interval, Duration::from_millis(20));
+        assert_eq!(stats.max_interval, Duration::from_millis(30));
+        assert_eq!(stats.mean_acquire, Duration::from_millis(10));
+        assert_eq!(stats.mean_latency, Some(Duration::from_millis(20)));
+        assert!((stats.fps() - 50.0).abs() < 1e-9);
+
+        assert_eq!(FrameStats::from_samples(&[]), FrameStats::default());
+    }
+
+    #[test]
+    fn test_burn_cpu_takes_at_least_the_duration() {
+        let start = Instant::now();
+        burn_cpu(Duration::from_millis(5));
+        assert!(start.elapsed() >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_gpu_load_shader_validates() {
+        let module = naga::front::wgsl::parse_str(GPU_LOAD_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+}
@@ -0,0 +1,321 @@
+use std::time::Duration;
+
+use crate::device_recovery::ResourceRecreate;
+use crate::frame_pacing::{
+    burn_cpu, present_mode_description, FrameSample, FrameStats, FrameTimer, GpuLoad,
+    PresentSettings, HISTORY_FRAMES, MAX_FRAME_LATENCY, PRESENT_MODES,
+};
+
+/// Largest artificial CPU cost per frame in milliseconds
+const MAX_CPU_COST_MS: f32 = 50.0;
+
+/// Largest artificial GPU cost per frame in loop iterations per invocation
+const MAX_GPU_ITERATIONS: u32 = 2_000_000;
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// UI panel to switch the window's present mode and frame latency at
+/// runtime, add artificial frame cost and chart the resulting frame timings
+pub struct FramePacingPanel {
+    /// Settings chosen in the UI
+    settings: PresentSettings,
+    /// Settings the surface is configured with
+    active: PresentSettings,
+    /// Present modes the surface supports
+    supported: Vec<wgpu::PresentMode>,
+    apply_requested: bool,
+    cpu_cost_ms: f32,
+    gpu_iterations: u32,
+    gpu_load: Option<GpuLoad>,
+    timer: FrameTimer,
+}
+
+impl Default for FramePacingPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FramePacingPanel {
+    pub fn new() -> Self {
+        Self {
+            settings: PresentSettings::default(),
+            active: PresentSettings::default(),
+            supported: vec![wgpu::PresentMode::Fifo],
+            apply_requested: false,
+            cpu_cost_ms: 0.0,
+            gpu_iterations: 0,
+            gpu_load: None,
+            timer: FrameTimer::new(),
+        }
+    }
+
+    /// Record the settings the surface was configured with and the present
+    /// modes it supports
+    pub fn set_active(&mut self, settings: PresentSettings, supported: &[wgpu::PresentMode]) {
+        self.settings = settings;
+        self.active = settings;
+        self.supported = supported.to_vec();
+    }
+
+    pub fn settings(&self) -> &PresentSettings {
+        &self.settings
+    }
+
+    /// Select new settings; they are applied to the surface before the next
+    /// frame
+    pub fn set_settings(&mut self, settings: PresentSettings) {
+        if settings != self.settings {
+            self.settings = settings;
+            self.apply_requested = true;
+        }
+    }
+
+    /// Returns the settings to reconfigure the surface with, once per change
+    pub fn take_apply_request(&mut self) -> Option<PresentSettings> {
+        std::mem::take(&mut self.apply_requested).then_some(self.settings)
+    }
+
+    pub fn is_supported(&self, mode: wgpu::PresentMode) -> bool {
+        self.supported.contains(&mode)
+    }
+
+    pub fn set_cpu_cost(&mut self, cost: Duration) {
+        self.cpu_cost_ms = (cost.as_secs_f32() * 1000.0).clamp(0.0, MAX_CPU_COST_MS);
+    }
+
+    pub fn set_gpu_iterations(&mut self, iterations: u32) {
+        self.gpu_iterations = iterations.min(MAX_GPU_ITERATIONS);
+    }
+
+    /// Timer the render loop reports each frame's stages to
+    pub fn timer_mut(&mut self) -> &mut FrameTimer {
+        &mut self.timer
+    }
+
+    pub fn samples(&self) -> Vec<FrameSample> {
+        self.timer.samples()
+    }
+
+    /// Spend the artificial CPU cost and submit the artificial GPU cost
+    ///
+    /// Called every frame, whichever tab is shown.
+    pub fn apply_load(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.cpu_cost_ms > 0.0 {
+            burn_cpu(Duration::from_secs_f32(self.cpu_cost_ms / 1000.0));
+        }
+        if self.gpu_iterations > 0 {
+            self.gpu_load
+                .get_or_insert_with(|| GpuLoad::new(device))
+                .submit(device, queue, self.gpu_iterations);
+        }
+    }
+
+    /// Render the panel
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🎞 Frame Pacing");
+        ui.separator();
+        ui.label(
+            "Switch the window's present mode and frame latency while it runs, make frames \
+             more expensive on the CPU or GPU and watch how frame time and latency respond.",
+        );
+        ui.add_space(10.0);
+
+        let mut settings = self.settings;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.label(egui::RichText::new("Present Mode").strong());
+            for mode in PRESENT_MODES {
+                let supported = self.is_supported(mode);
+                let response = ui.add_enabled(
+                    supported,
+                    egui::RadioButton::new(settings.present_mode == mode, format!("{:?}", mode)),
+                );
+                if response.clicked() {
+                    settings.present_mode = mode;
+                }
+                let description = present_mode_description(mode);
+                if supported {
+                    response.on_hover_text(description);
+                } else {
+                    response.on_disabled_hover_text(format!(
+                        "{}\n\nNot supported by this surface.",
+                        description
+                    ));
+                }
+            }
+            ui.label(
+                egui::RichText::new(present_mode_description(settings.present_mode))
+                    .small()
+                    .weak(),
+            );
+            ui.add_space(5.0);
+
+            egui::Grid::new("frame_pacing_settings")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Max frame latency:");
+                    ui.add(egui::Slider::new(
+                        &mut settings.desired_maximum_frame_latency,
+                        1..=MAX_FRAME_LATENCY,
+                    ))
+                    .on_hover_text(
+                        "desired_maximum_frame_latency: how many frames the CPU may queue ahead of the display",
+                    );
+                    ui.end_row();
+
+                    ui.label("CPU cost:");
+                    ui.add(
+                        egui::Slider::new(&mut self.cpu_cost_ms, 0.0..=MAX_CPU_COST_MS)
+                            .suffix(" ms"),
+                    )
+                    .on_hover_text("Busy-wait this long in every frame");
+                    ui.end_row();
+
+                    ui.label("GPU cost:");
+                    ui.add(
+                        egui::Slider::new(&mut self.gpu_iterations, 0..=MAX_GPU_ITERATIONS)
+                            .logarithmic(true)
+                            .suffix(" iterations"),
+                    )
+                    .on_hover_text("Loop iterations of a compute dispatch submitted every frame");
+                    ui.end_row();
+                });
+            if self.active != settings {
+                ui.label(
+                    egui::RichText::new("Applying to the surface...")
+                        .small()
+                        .weak(),
+                );
+            }
+            ui.add_space(10.0);
+
+            self.render_timings(ui);
+        });
+
+        self.set_settings(settings);
+    }
+
+    fn render_timings(&mut self, ui: &mut egui::Ui) {
+        let samples = self.timer.samples();
+        let stats = FrameStats::from_samples(&samples);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Timings").strong());
+            if ui.button("🔄 Reset").clicked() {
+                self.timer.clear();
+            }
+        });
+        egui::Grid::new("frame_pacing_stats")
+            .num_columns(2)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Frame rate:");
+                ui.monospace(format!("{:.1} fps", stats.fps()));
+                ui.end_row();
+                ui.label("Frame time (mean / max):");
+                ui.monospace(format!(
+                    "{:.2} / {:.2} ms",
+                    ms(stats.mean_interval),
+                    ms(stats.max_interval)
+                ));
+                ui.end_row();
+                ui.label("Waiting for a surface texture:")
+                    .on_hover_text("Time blocked in get_current_texture");
+                ui.monospace(format!("{:.2} ms", ms(stats.mean_acquire)));
+                ui.end_row();
+                ui.label("CPU time to submit:");
+                ui.monospace(format!("{:.2} ms", ms(stats.mean_cpu)));
+                ui.end_row();
+                ui.label("Latency to GPU done:")
+                    .on_hover_text("From the start of a frame until on_submitted_work_done reports its work finished");
+                match stats.mean_latency {
+                    Some(latency) => ui.monospace(format!("{:.2} ms", ms(latency))),
+                    None => ui.label("-"),
+                };
+                ui.end_row();
+            });
+        ui.add_space(10.0);
+
+        let series: [(&str, fn(&FrameSample) -> Option<Duration>); 4] = [
+            ("Frame time (ms)", |s| Some(s.interval)),
+            ("Acquire wait (ms)", |s| Some(s.acquire)),
+            ("CPU time (ms)", |s| Some(s.cpu)),
+            ("Latency (ms)", |s| s.latency),
+        ];
+        let timed: Vec<&FrameSample> = samples.iter().filter(|s| s.frame > 0).collect();
+        let lines: Vec<egui_plot::Line> = series
+            .iter()
+            .map(|(name, value)| {
+                let points: egui_plot::PlotPoints = timed
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(x, s)| value(s).map(|v| [x as f64, ms(v)]))
+                    .collect();
+                egui_plot::Line::new(*name, points)
+            })
+            .collect();
+
+        egui_plot::Plot::new("frame_pacing_plot")
+            .height(240.0)
+            .legend(egui_plot::Legend::default())
+            .include_x(HISTORY_FRAMES as f64)
+            .include_y(0.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                for line in lines {
+                    plot_ui.line(line);
+                }
+            });
+    }
+}
+
+impl ResourceRecreate for FramePacingPanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // The load pipeline belongs to the old device; it is created again
+        // on the next frame
+        self.gpu_load = None;
+        self.timer.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_settings_request_apply_once() {
+        let mut panel = FramePacingPanel::new();
+        panel.set_active(
+            PresentSettings::default(),
+            &[wgpu::PresentMode::Fifo, wgpu::PresentMode::Mailbox],
+        );
+        assert_eq!(panel.take_apply_request(), None);
+
+        panel.set_settings(PresentSettings::default());
+        assert_eq!(panel.take_apply_request(), None);
+
+        let mailbox = PresentSettings {
+            present_mode: wgpu::PresentMode::Mailbox,
+            desired_maximum_frame_latency: 1,
+        };
+        panel.set_settings(mailbox);
+        assert_eq!(panel.take_apply_request(), Some(mailbox));
+        assert_eq!(panel.take_apply_request(), None);
+        assert!(panel.is_supported(wgpu::PresentMode::Mailbox));
+        assert!(!panel.is_supported(wgpu::PresentMode::Immediate));
+    }
+
+    #[test]
+    fn test_load_is_clamped() {
+        let mut panel = FramePacingPanel::new();
+        panel.set_cpu_cost(Duration::from_secs(1));
+        assert_eq!(panel.cpu_cost_ms, MAX_CPU_COST_MS);
+        panel.set_gpu_iterations(u32::MAX);
+        assert_eq!(panel.gpu_iterations, MAX_GPU_ITERATIONS);
+    }
+}
@@ -0,0 +1,591 @@
+//! Parameterized primitive meshes
+//!
+//! Each [`Primitive`] generates an indexed triangle list with positions,
+//! normals, UVs and tangents. The primitives fit the unit cube centred on the
+//! origin, like the preview cube, and wind their triangles counter-clockwise
+//! seen from outside. Tangents follow the convention of
+//! [`tangent_debug`](crate::tangent_debug): along +u, with the bitangent
+//! `cross(normal, tangent) * w` pointing along -v.
+
+use crate::model_loader::{Mesh, ModelData, ModelVertex};
+use std::collections::HashMap;
+use std::f32::consts::{PI, TAU};
+
+/// Most segments, sides and rings of the sphere, torus and cylinder
+pub const MAX_SEGMENTS: u32 = 256;
+/// Most subdivisions of the plane grid along each side
+pub const MAX_GRID_SUBDIVISIONS: u32 = 256;
+/// Most subdivisions of the icosphere; each one quadruples the triangles
+pub const MAX_ICOSPHERE_SUBDIVISIONS: u32 = 6;
+
+/// Vertex of a generated primitive
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GeometryVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+    /// Tangent with the sign of the bitangent in w
+    pub tangent: [f32; 4],
+}
+
+/// Indexed triangle list of a primitive
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Geometry {
+    pub vertices: Vec<GeometryVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl Geometry {
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// A single-mesh model without materials, for the model preview
+    pub fn to_model_data(&self) -> ModelData {
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|v| ModelVertex::new(v.position, v.normal, v.uv))
+            .collect();
+        ModelData {
+            meshes: vec![Mesh {
+                vertices,
+                indices: self.indices.clone(),
+                material_index: None,
+            }],
+            materials: Vec::new(),
+            textures: Vec::new(),
+            vertex_count: self.vertices.len() as u32,
+            index_count: self.indices.len() as u32,
+        }
+    }
+
+    /// Two triangles for each cell of a `columns` by `rows` grid of vertices
+    /// laid out row by row, `u` along the rows and `v` down the columns
+    fn push_grid_indices(&mut self, base: u32, columns: u32, rows: u32) {
+        let stride = columns + 1;
+        for row in 0..rows {
+            for column in 0..columns {
+                let a = base + row * stride + column;
+                let (b, c) = (a + 1, a + stride);
+                let d = c + 1;
+                self.indices.extend_from_slice(&[a, c, b, b, c, d]);
+            }
+        }
+    }
+
+    /// Drop triangles with two vertices at the same position, like those
+    /// touching the poles of a UV sphere
+    fn remove_degenerate(&mut self) {
+        let vertices = &self.vertices;
+        let indices = self
+            .indices
+            .chunks_exact(3)
+            .filter(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| vertices[triangle[i] as usize].position);
+                a != b && b != c && c != a
+            })
+            .flatten()
+            .copied()
+            .collect();
+        self.indices = indices;
+    }
+}
+
+/// A generated mesh and its parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Primitive {
+    /// Latitude-longitude sphere
+    Sphere { segments: u32, rings: u32 },
+    /// Ring around the Y axis; `tube_radius` of at most 0.25
+    Torus {
+        tube_radius: f32,
+        segments: u32,
+        sides: u32,
+    },
+    /// Cylinder along the Y axis, optionally closed
+    Cylinder { segments: u32, caps: bool },
+    /// Subdivided unit square in the XZ plane, facing up
+    Grid { subdivisions: u32 },
+    /// Subdivided icosahedron, with evenly sized triangles
+    Icosphere { subdivisions: u32 },
+}
+
+impl Primitive {
+    /// Every kind of primitive with its default parameters
+    pub const ALL: [Primitive; 5] = [
+        Primitive::Sphere {
+            segments: 32,
+            rings: 16,
+        },
+        Primitive::Torus {
+            tube_radius: 0.15,
+            segments: 48,
+            sides: 16,
+        },
+        Primitive::Cylinder {
+            segments: 32,
+            caps: true,
+        },
+        Primitive::Grid { subdivisions: 8 },
+        Primitive::Icosphere { subdivisions: 2 },
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Primitive::Sphere { .. } => "Sphere",
+            Primitive::Torus { .. } => "Torus",
+            Primitive::Cylinder { .. } => "Cylinder",
+            Primitive::Grid { .. } => "Plane Grid",
+            Primitive::Icosphere { .. } => "Icosphere",
+        }
+    }
+
+    /// Whether both are the same kind of primitive, whatever the parameters
+    pub fn same_kind(self, other: Primitive) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+
+    /// Generate the mesh; parameters out of range are clamped
+    pub fn generate(self) -> Geometry {
+        match self {
+            Primitive::Sphere { segments, rings } => uv_sphere(
+                segments.clamp(3, MAX_SEGMENTS),
+                rings.clamp(2, MAX_SEGMENTS),
+            ),
+            Primitive::Torus {
+                tube_radius,
+                segments,
+                sides,
+            } => torus(
+                tube_radius.clamp(0.01, 0.25),
+                segments.clamp(3, MAX_SEGMENTS),
+                sides.clamp(3, MAX_SEGMENTS),
+            ),
+            Primitive::Cylinder { segments, caps } => {
+                cylinder(segments.clamp(3, MAX_SEGMENTS), caps)
+            }
+            Primitive::Grid { subdivisions } => {
+                plane_grid(subdivisions.clamp(1, MAX_GRID_SUBDIVISIONS))
+            }
+            Primitive::Icosphere { subdivisions } => {
+                icosphere(subdivisions.min(MAX_ICOSPHERE_SUBDIVISIONS))
+            }
+        }
+    }
+
+    /// Sliders for the parameters
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        match self {
+            Primitive::Sphere { segments, rings } => {
+                ui.add(egui::Slider::new(segments, 3..=MAX_SEGMENTS).text("Segments"));
+                ui.add(egui::Slider::new(rings, 2..=MAX_SEGMENTS).text("Rings"));
+            }
+            Primitive::Torus {
+                tube_radius,
+                segments,
+                sides,
+            } => {
+                ui.add(egui::Slider::new(tube_radius, 0.01..=0.25).text("Tube radius"));
+                ui.add(egui::Slider::new(segments, 3..=MAX_SEGMENTS).text("Segments"));
+                ui.add(egui::Slider::new(sides, 3..=MAX_SEGMENTS).text("Sides"));
+            }
+            Primitive::Cylinder { segments, caps } => {
+                ui.add(egui::Slider::new(segments, 3..=MAX_SEGMENTS).text("Segments"));
+                ui.checkbox(caps, "Caps");
+            }
+            Primitive::Grid { subdivisions } => {
+                ui.add(
+                    egui::Slider::new(subdivisions, 1..=MAX_GRID_SUBDIVISIONS).text("Subdivisions"),
+                );
+            }
+            Primitive::Icosphere { subdivisions } => {
+                ui.add(
+                    egui::Slider::new(subdivisions, 0..=MAX_ICOSPHERE_SUBDIVISIONS)
+                        .text("Subdivisions"),
+                );
+            }
+        }
+    }
+}
+
+/// Vertex on the sphere of radius 0.5 in unit direction `n`, with longitude
+/// `u` and latitude `v` running from the top pole down
+///
+/// The seam is at -Z; the tangent depends only on `u`, so vertices at the
+/// poles get the tangent of their column.
+fn sphere_vertex(n: [f32; 3], u: f32, v: f32) -> GeometryVertex {
+    let (sin_theta, cos_theta) = (TAU * (u - 0.5)).sin_cos();
+    GeometryVertex {
+        position: n.map(|c| c * 0.5),
+        normal: n,
+        uv: [u, v],
+        tangent: [cos_theta, 0.0, -sin_theta, 1.0],
+    }
+}
+
+/// Longitude and latitude of unit direction `n`, as in [`sphere_vertex`]
+fn sphere_uv(n: [f32; 3]) -> [f32; 2] {
+    [
+        0.5 + n[0].atan2(n[2]) / TAU,
+        n[1].clamp(-1.0, 1.0).acos() / PI,
+    ]
+}
+
+fn uv_sphere(segments: u32, rings: u32) -> Geometry {
+    let mut geometry = Geometry::default();
+    for ring in 0..=rings {
+        let v = ring as f32 / rings as f32;
+        // Exact poles, so their triangles are recognised as degenerate
+        let (sin_phi, cos_phi) = match ring {
+            0 => (0.0, 1.0),
+            r if r == rings => (0.0, -1.0),
+            _ => (PI * v).sin_cos(),
+        };
+        for segment in 0..=segments {
+            let u = segment as f32 / segments as f32;
+            let (sin_theta, cos_theta) = (TAU * (u - 0.5)).sin_cos();
+            let n = [sin_phi * sin_theta, cos_phi, sin_phi * cos_theta];
+            geometry.vertices.push(sphere_vertex(n, u, v));
+        }
+    }
+    geometry.push_grid_indices(0, segments, rings);
+    geometry.remove_degenerate();
+    geometry
+}
+
+fn torus(tube_radius: f32, segments: u32, sides: u32) -> Geometry {
+    let ring_radius = 0.5 - tube_radius;
+    let mut geometry = Geometry::default();
+    for side in 0..=sides {
+        let v = side as f32 / sides as f32;
+        // Around the tube from the outer equator over the top, so the
+        // bitangent follows -v
+        let (sin_psi, cos_psi) = (-TAU * v).sin_cos();
+        for segment in 0..=segments {
+            let u = segment as f32 / segments as f32;
+            let (sin_theta, cos_theta) = (TAU * u).sin_cos();
+            let normal = [cos_psi * sin_theta, sin_psi, cos_psi * cos_theta];
+            let center = [ring_radius * sin_theta, 0.0, ring_radius * cos_theta];
+            geometry.vertices.push(GeometryVertex {
+                position: std::array::from_fn(|i| center[i] + tube_radius * normal[i]),
+                normal,
+                uv: [u, v],
+                tangent: [cos_theta, 0.0, -sin_theta, 1.0],
+            });
+        }
+    }
+    geometry.push_grid_indices(0, segments, sides);
+    geometry
+}
+
+fn cylinder(segments: u32, caps: bool) -> Geometry {
+    let mut geometry = Geometry::default();
+    for row in 0..=1 {
+        let v = row as f32;
+        for segment in 0..=segments {
+            let u = segment as f32 / segments as f32;
+            let (sin_theta, cos_theta) = (TAU * (u - 0.5)).sin_cos();
+            geometry.vertices.push(GeometryVertex {
+                position: [0.5 * sin_theta, 0.5 - v, 0.5 * cos_theta],
+                normal: [sin_theta, 0.0, cos_theta],
+                uv: [u, v],
+                tangent: [cos_theta, 0.0, -sin_theta, 1.0],
+            });
+        }
+    }
+    geometry.push_grid_indices(0, segments, 1);
+
+    if caps {
+        // Fans around the centre of each end, mapped from above with +u
+        // along +X; v runs towards +Z on top and towards -Z below
+        for (y, v_sign) in [(0.5f32, 1.0f32), (-0.5, -1.0)] {
+            let center = geometry.vertices.len() as u32;
+            let normal = [0.0, 2.0 * y, 0.0];
+            let mut push = |x: f32, z: f32| {
+                geometry.vertices.push(GeometryVertex {
+                    position: [x, y, z],
+                    normal,
+                    uv: [0.5 + x, 0.5 + v_sign * z],
+                    tangent: [1.0, 0.0, 0.0, 1.0],
+                });
+            };
+            push(0.0, 0.0);
+            for segment in 0..segments {
+                let (sin_theta, cos_theta) = (TAU * segment as f32 / segments as f32).sin_cos();
+                push(0.5 * sin_theta, 0.5 * cos_theta);
+            }
+            for segment in 0..segments {
+                let a = center + 1 + segment;
+                let b = center + 1 + (segment + 1) % segments;
+                let triangle = if y > 0.0 {
+                    [center, a, b]
+                } else {
+                    [center, b, a]
+                };
+                geometry.indices.extend_from_slice(&triangle);
+            }
+        }
+    }
+    geometry
+}
+
+fn plane_grid(subdivisions: u32) -> Geometry {
+    let mut geometry = Geometry::default();
+    for row in 0..=subdivisions {
+        let v = row as f32 / subdivisions as f32;
+        for column in 0..=subdivisions {
+            let u = column as f32 / subdivisions as f32;
+            geometry.vertices.push(GeometryVertex {
+                position: [u - 0.5, 0.0, v - 0.5],
+                normal: [0.0, 1.0, 0.0],
+                uv: [u, v],
+                tangent: [1.0, 0.0, 0.0, 1.0],
+            });
+        }
+    }
+    geometry.push_grid_indices(0, subdivisions, subdivisions);
+    geometry
+}
+
+fn icosphere(subdivisions: u32) -> Geometry {
+    let t = (1.0 + 5.0f32.sqrt()) / 2.0;
+    let mut directions: Vec<[f32; 3]> = [
+        [-1.0, t, 0.0],
+        [1.0, t, 0.0],
+        [-1.0, -t, 0.0],
+        [1.0, -t, 0.0],
+        [0.0, -1.0, t],
+        [0.0, 1.0, t],
+        [0.0, -1.0, -t],
+        [0.0, 1.0, -t],
+        [t, 0.0, -1.0],
+        [t, 0.0, 1.0],
+        [-t, 0.0, -1.0],
+        [-t, 0.0, 1.0],
+    ]
+    .into_iter()
+    .map(crate::math_utils::normalize)
+    .collect();
+    let mut triangles: Vec<[u32; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints = HashMap::new();
+        let mut midpoint = |a: u32, b: u32| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let (pa, pb) = (directions[a as usize], directions[b as usize]);
+                directions.push(crate::math_utils::normalize(std::array::from_fn(|i| {
+                    pa[i] + pb[i]
+                })));
+                directions.len() as u32 - 1
+            })
+        };
+        triangles = triangles
+            .iter()
+            .flat_map(|&[a, b, c]| {
+                let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    let mut geometry = Geometry {
+        vertices: directions
+            .iter()
+            .map(|&n| {
+                let [u, v] = sphere_uv(n);
+                sphere_vertex(n, u, v)
+            })
+            .collect(),
+        indices: Vec::with_capacity(triangles.len() * 3),
+    };
+    let is_pole = |n: [f32; 3]| n[0].abs() < 1e-6 && n[2].abs() < 1e-6;
+    let mut seam_copies: HashMap<u32, u32> = HashMap::new();
+    for mut triangle in triangles {
+        // Triangles across the seam at -Z would interpolate u over the whole
+        // texture; give their vertices near u = 0 copies at u + 1
+        let longitudes: Vec<f32> = triangle
+            .iter()
+            .map(|&i| geometry.vertices[i as usize])
+            .filter(|vertex| !is_pole(vertex.normal))
+            .map(|vertex| vertex.uv[0])
+            .collect();
+        let (min, max) = longitudes
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), &u| (lo.min(u), hi.max(u)));
+        if max - min > 0.5 {
+            for index in &mut triangle {
+                let vertex = geometry.vertices[*index as usize];
+                if is_pole(vertex.normal) || vertex.uv[0] >= 0.5 {
+                    continue;
+                }
+                *index = *seam_copies.entry(*index).or_insert_with(|| {
+                    geometry.vertices.push(sphere_vertex(
+                        vertex.normal,
+                        vertex.uv[0] + 1.0,
+                        vertex.uv[1],
+                    ));
+                    geometry.vertices.len() as u32 - 1
+                });
+            }
+        }
+
+        // A pole has no longitude of its own; each triangle gets a copy at
+        // the average of its other two vertices
+        for corner in 0..3 {
+            let vertex = geometry.vertices[triangle[corner] as usize];
+            if !is_pole(vertex.normal) {
+                continue;
+            }
+            let u = (1..3)
+                .map(|k| geometry.vertices[triangle[(corner + k) % 3] as usize].uv[0])
+                .sum::<f32>()
+                / 2.0;
+            geometry
+                .vertices
+                .push(sphere_vertex(vertex.normal, u, vertex.uv[1]));
+            triangle[corner] = geometry.vertices.len() as u32 - 1;
+        }
+        geometry.indices.extend_from_slice(&triangle);
+    }
+    geometry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_utils::{cross, dot};
+    use crate::tangent_debug::vertex_tangents;
+
+    fn length(v: [f32; 3]) -> f32 {
+        dot(v, v).sqrt()
+    }
+
+    #[test]
+    fn test_primitives_are_well_formed() {
+        for primitive in Primitive::ALL {
+            let geometry = primitive.generate();
+            let name = primitive.name();
+            assert!(geometry.triangle_count() > 0, "{}", name);
+            assert_eq!(geometry.indices.len() % 3, 0, "{}", name);
+            assert!(
+                geometry
+                    .indices
+                    .iter()
+                    .all(|&i| (i as usize) < geometry.vertices.len()),
+                "{}",
+                name
+            );
+
+            for vertex in &geometry.vertices {
+                let t = [vertex.tangent[0], vertex.tangent[1], vertex.tangent[2]];
+                assert!((length(vertex.normal) - 1.0).abs() < 1e-4, "{}", name);
+                assert!((length(t) - 1.0).abs() < 1e-4, "{}", name);
+                assert!(dot(t, vertex.normal).abs() < 1e-4, "{}", name);
+                assert_eq!(vertex.tangent[3].abs(), 1.0, "{}", name);
+                assert!(vertex.position.iter().all(|c| c.abs() <= 0.5 + 1e-5));
+            }
+
+            // Counter-clockwise seen from outside
+            for triangle in geometry.indices.chunks_exact(3) {
+                let [a, b, c] = [0, 1, 2].map(|i| geometry.vertices[triangle[i] as usize]);
+                let e1: [f32; 3] = std::array::from_fn(|i| b.position[i] - a.position[i]);
+                let e2: [f32; 3] = std::array::from_fn(|i| c.position[i] - a.position[i]);
+                let normal: [f32; 3] =
+                    std::array::from_fn(|i| a.normal[i] + b.normal[i] + c.normal[i]);
+                assert!(dot(cross(e1, e2), normal) > 0.0, "{} {:?}", name, triangle);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tangents_match_uv_derivatives() {
+        for primitive in Primitive::ALL {
+            let model = primitive.generate().to_model_data();
+            let mesh = &model.meshes[0];
+            let derived = vertex_tangents(&mesh.vertices, &mesh.indices);
+            let geometry = primitive.generate();
+            for (vertex, derived) in geometry.vertices.iter().zip(derived) {
+                // Next to the poles the interpolated u of the triangles is distorted
+                let round = matches!(
+                    primitive,
+                    Primitive::Sphere { .. } | Primitive::Icosphere { .. }
+                );
+                if round && vertex.normal[1].abs() > 0.9 {
+                    continue;
+                }
+                let t = [vertex.tangent[0], vertex.tangent[1], vertex.tangent[2]];
+                let d = [derived[0], derived[1], derived[2]];
+                assert!(dot(t, d) > 0.95, "{} {:?}", primitive.name(), vertex);
+                assert_eq!(vertex.tangent[3], derived[3], "{}", primitive.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_sphere_counts_and_radius() {
+        let geometry = Primitive::Sphere {
+            segments: 8,
+            rings: 4,
+        }
+        .generate();
+        assert_eq!(geometry.vertices.len(), 9 * 5);
+        // A fan of single triangles at each pole, quads in between
+        assert_eq!(geometry.triangle_count(), 8 * 2 + 8 * 2 * 2);
+        for vertex in &geometry.vertices {
+            assert!((length(vertex.position) - 0.5).abs() < 1e-5);
+        }
+
+        let grid = Primitive::Grid { subdivisions: 3 }.generate();
+        assert_eq!(grid.vertices.len(), 16);
+        assert_eq!(grid.triangle_count(), 18);
+    }
+
+    #[test]
+    fn test_icosphere_subdivision() {
+        for subdivisions in 0..=3 {
+            let geometry = Primitive::Icosphere { subdivisions }.generate();
+            assert_eq!(geometry.triangle_count(), 20 * 4usize.pow(subdivisions));
+            for vertex in &geometry.vertices {
+                assert!((length(vertex.position) - 0.5).abs() < 1e-5);
+            }
+            // No triangle stretches across the seam
+            for triangle in geometry.indices.chunks_exact(3) {
+                let us = [0, 1, 2].map(|i| geometry.vertices[triangle[i] as usize].uv[0]);
+                let spread = us.iter().fold(f32::MIN, |m, &u| m.max(u))
+                    - us.iter().fold(f32::MAX, |m, &u| m.min(u));
+                assert!(spread <= 0.5, "{:?}", us);
+            }
+        }
+        // Parameters beyond the limit are clamped
+        assert_eq!(
+            Primitive::Icosphere { subdivisions: 99 }.generate(),
+            Primitive::Icosphere {
+                subdivisions: MAX_ICOSPHERE_SUBDIVISIONS
+            }
+            .generate()
+        );
+    }
+}
@@ -0,0 +1,703 @@
+//! Grass generated by a compute shader every frame
+//!
+//! A compute pass writes the vertices of every blade into a storage
+//! buffer, bending each blade by a wind that travels across the field. The
+//! render pass draws the blades instanced, one triangle strip per instance,
+//! pulling the vertices from the same buffer by instance and vertex index.
+//!
+//! Like foliage drawn from a texture, the outline of a blade comes from
+//! alpha rather than geometry. Rendered with 4x MSAA, alpha to coverage
+//! turns that alpha into partial sample coverage and smooth edges; the
+//! alpha test fallback keeps or drops whole pixels and shimmers as the
+//! blades sway. This is the effect of the Alpha to Coverage toggle in the
+//! render pipeline panel's multisample state.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::Mat4;
+use crate::upload_belt;
+
+/// Segments along a blade
+pub const SEGMENTS: u32 = 4;
+
+/// Vertices of the triangle strip of one blade: two per segment boundary
+pub const VERTICES_PER_BLADE: u32 = 2 * (SEGMENTS + 1);
+
+/// Largest number of blades; the vertex buffer is allocated for this many
+pub const MAX_BLADES: u32 = 200_000;
+
+/// Blades when the example starts
+pub const DEFAULT_BLADES: u32 = 40_000;
+
+/// Side of the square field
+pub const FIELD_SIZE: f32 = 12.0;
+
+const BLADE_HEIGHT: f32 = 0.5;
+const BLADE_WIDTH: f32 = 0.06;
+const WIND_DIRECTION: [f32; 2] = [1.0, 0.35];
+
+/// Samples per pixel; alpha to coverage needs more than one
+pub const SAMPLE_COUNT: u32 = 4;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub const GRASS_GENERATE_SHADER: &str = r#"// Grass generation: one thread per blade writes the blade's vertices,
+// bent by the wind at the current time, for the render pass to draw.
+
+const SEGMENTS: u32 = 4u;
+const VERTICES_PER_BLADE: u32 = 10u;
+
+struct GrassVertex {
+    position: vec3<f32>,
+    // -1 on the left edge of the blade, 1 on the right
+    side: f32,
+    normal: vec3<f32>,
+    // 0 at the root, 1 at the tip
+    height: f32,
+}
+
+struct Params {
+    // Direction the wind blows towards, on the XZ plane
+    wind_direction: vec2<f32>,
+    wind_strength: f32,
+    time: f32,
+    blade_count: u32,
+    field_size: f32,
+    blade_height: f32,
+    blade_width: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> vertices: array<GrassVertex>;
+
+fn hash(n: u32) -> f32 {
+    var x = n * 747796405u + 2891336453u;
+    x = ((x >> ((x >> 28u) + 4u)) ^ x) * 277803737u;
+    x = (x >> 22u) ^ x;
+    return f32(x) / 4294967295.0;
+}
+
+@compute @workgroup_size(64)
+fn generate_blades(@builtin(global_invocation_id) id: vec3<u32>) {
+    let blade = id.x;
+    if blade >= params.blade_count {
+        return;
+    }
+
+    // Jittered grid over the field
+    let side = u32(ceil(sqrt(f32(params.blade_count))));
+    let cell = vec2<f32>(f32(blade % side), f32(blade / side));
+    let jitter = vec2<f32>(hash(blade * 4u), hash(blade * 4u + 1u));
+    let root_xz = ((cell + jitter) / f32(side) - 0.5) * params.field_size;
+    let root = vec3<f32>(root_xz.x, 0.0, root_xz.y);
+
+    let height = params.blade_height * (0.6 + 0.8 * hash(blade * 4u + 2u));
+    let angle = hash(blade * 4u + 3u) * 6.2831853;
+    let across = vec3<f32>(cos(angle), 0.0, sin(angle));
+
+    // Waves of wind travel across the field, each blade sways a little
+    // out of phase
+    let wind = normalize(params.wind_direction);
+    let phase = dot(root_xz, wind) * 0.4 - params.time * 2.0;
+    let gust = 0.6 + 0.4 * sin(phase) + 0.15 * sin(phase * 2.7 + angle);
+    let bend = params.wind_strength * gust;
+    let lean = vec3<f32>(wind.x, 0.0, wind.y);
+
+    let base = blade * VERTICES_PER_BLADE;
+    for (var segment = 0u; segment <= SEGMENTS; segment++) {
+        let t = f32(segment) / f32(SEGMENTS);
+        // Quadratic bend; the blade shortens vertically to keep its length
+        let sway = bend * t * t;
+        let point = root + lean * sway * height
+            + vec3<f32>(0.0, height * t * (1.0 - 0.4 * bend * bend * t * t), 0.0);
+        let tangent = lean * 2.0 * bend * t + vec3<f32>(0.0, 1.0 - 1.2 * bend * bend * t * t, 0.0);
+        let normal = normalize(cross(across, tangent));
+        let half_width = params.blade_width * 0.5;
+        for (var edge = 0u; edge < 2u; edge++) {
+            let s = f32(edge) * 2.0 - 1.0;
+            var vertex: GrassVertex;
+            vertex.position = point + across * s * half_width;
+            vertex.side = s;
+            vertex.normal = normal;
+            vertex.height = t;
+            vertices[base + segment * 2u + edge] = vertex;
+        }
+    }
+}
+"#;
+
+pub const GRASS_RENDER_SHADER: &str = r#"// Compute-Generated Grass Example
+// A compute pass writes the vertices of every blade each frame; this
+// shader draws them instanced, one triangle strip per blade, pulling the
+// vertices from the storage buffer. The blade's outline comes from alpha,
+// like a foliage texture: alpha to coverage turns it into smooth MSAA
+// coverage, the alpha test fallback discards and aliases.
+
+const VERTICES_PER_BLADE: u32 = 10u;
+
+struct GrassVertex {
+    position: vec3<f32>,
+    side: f32,
+    normal: vec3<f32>,
+    height: f32,
+}
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    field_size: f32,
+    // Discard below half alpha instead of relying on alpha to coverage
+    alpha_test: u32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var<storage, read> vertices: array<GrassVertex>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) side: f32,
+    @location(2) height: f32,
+    @location(3) tint: f32,
+}
+
+fn hash(n: u32) -> f32 {
+    var x = n * 747796405u + 2891336453u;
+    x = ((x >> ((x >> 28u) + 4u)) ^ x) * 277803737u;
+    x = (x >> 22u) ^ x;
+    return f32(x) / 4294967295.0;
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) blade: u32,
+) -> VertexOutput {
+    let vertex = vertices[blade * VERTICES_PER_BLADE + vertex_index];
+    var out: VertexOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(vertex.position, 1.0);
+    out.normal = vertex.normal;
+    out.side = vertex.side;
+    out.height = vertex.height;
+    out.tint = hash(blade * 7u + 5u);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput, @builtin(front_facing) front: bool) -> @location(0) vec4<f32> {
+    // Blade outline: full width at the root, tapering to a point
+    let width = pow(1.0 - in.height, 0.6);
+    let edge = fwidth(in.side);
+    let alpha = clamp((width - abs(in.side)) / max(edge, 1e-4) + 0.5, 0.0, 1.0);
+    if uniforms.alpha_test != 0u && alpha < 0.5 {
+        discard;
+    }
+
+    var normal = normalize(in.normal);
+    if !front {
+        normal = -normal;
+    }
+    let light = normalize(vec3<f32>(0.3, 1.0, 0.4));
+    let diffuse = 0.35 + 0.65 * abs(dot(normal, light));
+    let root = vec3<f32>(0.05, 0.18, 0.03);
+    let tip = mix(vec3<f32>(0.35, 0.65, 0.12), vec3<f32>(0.65, 0.7, 0.25), in.tint);
+    let color = mix(root, tip, in.height) * diffuse;
+    return vec4<f32>(color, alpha);
+}
+
+@vertex
+fn vs_ground(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    var corners = array<vec2<f32>, 4>(
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, -0.5),
+        vec2<f32>(-0.5, 0.5),
+        vec2<f32>(0.5, 0.5),
+    );
+    let corner = corners[index] * uniforms.field_size;
+    return uniforms.view_proj * vec4<f32>(corner.x, 0.0, corner.y, 1.0);
+}
+
+@fragment
+fn fs_ground() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.12, 0.08, 0.04, 1.0);
+}
+"#;
+
+/// How the alpha-shaped blade outlines become coverage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageMode {
+    /// Alpha picks how many of the MSAA samples the fragment covers
+    AlphaToCoverage,
+    /// Fragments below half alpha are discarded, all samples or none
+    AlphaTest,
+}
+
+impl CoverageMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CoverageMode::AlphaToCoverage => "Alpha to coverage",
+            CoverageMode::AlphaTest => "Alpha test (discard)",
+        }
+    }
+}
+
+/// A vertex written by the generation pass, laid out like `GrassVertex`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GrassVertex {
+    pub position: [f32; 3],
+    /// -1 on the left edge of the blade, 1 on the right
+    pub side: f32,
+    pub normal: [f32; 3],
+    /// 0 at the root, 1 at the tip
+    pub height: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct GenerateParams {
+    wind_direction: [f32; 2],
+    wind_strength: f32,
+    time: f32,
+    blade_count: u32,
+    field_size: f32,
+    blade_height: f32,
+    blade_width: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: Mat4,
+    field_size: f32,
+    alpha_test: u32,
+    _padding: [u32; 2],
+}
+
+/// Renderer of the grass example
+pub struct GrassField {
+    generate_pipeline: wgpu::ComputePipeline,
+    /// Grass pipelines, with and without alpha to coverage
+    coverage_pipeline: wgpu::RenderPipeline,
+    alpha_test_pipeline: wgpu::RenderPipeline,
+    ground_pipeline: wgpu::RenderPipeline,
+    generate_bind_group: wgpu::BindGroup,
+    render_bind_group: wgpu::BindGroup,
+    params_buffer: wgpu::Buffer,
+    uniform_buffer: wgpu::Buffer,
+    color_format: wgpu::TextureFormat,
+    msaa_view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+    size: (u32, u32),
+    blade_count: u32,
+    /// How far the wind bends the blades, 0 to 1
+    pub wind_strength: f32,
+    pub coverage: CoverageMode,
+}
+
+impl GrassField {
+    /// Create the renderer drawing into `color_format` targets of `width`x`height`
+    pub fn new(
+        device: &wgpu::Device,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let generate_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Grass Generate Shader"),
+            source: wgpu::ShaderSource::Wgsl(GRASS_GENERATE_SHADER.into()),
+        });
+        let render_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Grass Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(GRASS_RENDER_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let vertex_size = std::mem::size_of::<GrassVertex>() as u64;
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Grass Vertex Buffer"),
+            size: MAX_BLADES as u64 * VERTICES_PER_BLADE as u64 * vertex_size,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Grass Generate Params"),
+            size: std::mem::size_of::<GenerateParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Grass Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Writable storage is not allowed in the vertex stage, so the compute
+        // and render passes see the vertices through separate layouts
+        let layout_entries = |uniform_visibility, storage_visibility, read_only| {
+            [
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: uniform_visibility,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: storage_visibility,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only },
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(vertex_size),
+                    },
+                    count: None,
+                },
+            ]
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let generate_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Grass Generate Bind Group Layout"),
+            entries: &layout_entries(
+                wgpu::ShaderStages::COMPUTE,
+                wgpu::ShaderStages::COMPUTE,
+                false,
+            ),
+        });
+        let render_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Grass Render Bind Group Layout"),
+            entries: &layout_entries(
+                wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                wgpu::ShaderStages::VERTEX,
+                true,
+            ),
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let generate_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Grass Generate Bind Group"),
+            layout: &generate_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: vertex_buffer.as_entire_binding(),
+                },
+            ],
+        });
+        let render_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Grass Render Bind Group"),
+            layout: &render_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: vertex_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let generate_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Grass Generate Pipeline Layout"),
+                bind_group_layouts: &[Some(&generate_layout)],
+                immediate_size: 0,
+            });
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Grass Render Pipeline Layout"),
+                bind_group_layouts: &[Some(&render_layout)],
+                immediate_size: 0,
+            });
+
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let generate_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Grass Generate Pipeline"),
+            layout: Some(&generate_pipeline_layout),
+            module: &generate_shader,
+            entry_point: Some("generate_blades"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        let render_pipeline = |label, entry_points: [&str; 2], alpha_to_coverage_enabled| {
+            tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&render_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &render_shader,
+                    entry_point: Some(entry_points[0]),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &render_shader,
+                    entry_point: Some(entry_points[1]),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                // Blades are seen from both sides
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: DEPTH_FORMAT,
+                    depth_write_enabled: Some(true),
+                    depth_compare: Some(wgpu::CompareFunction::Less),
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled,
+                },
+                multiview_mask: None,
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+        let coverage_pipeline = render_pipeline(
+            "Grass Pipeline (Alpha to Coverage)",
+            ["vs_main", "fs_main"],
+            true,
+        );
+        let alpha_test_pipeline =
+            render_pipeline("Grass Pipeline (Alpha Test)", ["vs_main", "fs_main"], false);
+        let ground_pipeline =
+            render_pipeline("Grass Ground Pipeline", ["vs_ground", "fs_ground"], false);
+
+        let (msaa_view, depth_view) = create_targets(device, color_format, width, height);
+        Self {
+            generate_pipeline,
+            coverage_pipeline,
+            alpha_test_pipeline,
+            ground_pipeline,
+            generate_bind_group,
+            render_bind_group,
+            params_buffer,
+            uniform_buffer,
+            color_format,
+            msaa_view,
+            depth_view,
+            size: (width, height),
+            blade_count: DEFAULT_BLADES,
+            wind_strength: 0.5,
+            coverage: CoverageMode::AlphaToCoverage,
+        }
+    }
+
+    /// Grow or shrink the field, clamped to [`MAX_BLADES`]
+    pub fn set_blade_count(&mut self, count: u32) {
+        self.blade_count = count.min(MAX_BLADES);
+    }
+
+    pub fn blade_count(&self) -> u32 {
+        self.blade_count
+    }
+
+    /// Triangles drawn per frame
+    pub fn triangles(&self) -> u32 {
+        self.blade_count * (VERTICES_PER_BLADE - 2)
+    }
+
+    /// Bytes of vertices the generation pass writes per frame
+    pub fn generated_bytes(&self) -> u64 {
+        self.blade_count as u64
+            * VERTICES_PER_BLADE as u64
+            * std::mem::size_of::<GrassVertex>() as u64
+    }
+
+    /// Update the camera and the wind time
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: Mat4, time: f32) {
+        let params = GenerateParams {
+            wind_direction: WIND_DIRECTION,
+            wind_strength: self.wind_strength,
+            time,
+            blade_count: self.blade_count,
+            field_size: FIELD_SIZE,
+            blade_height: BLADE_HEIGHT,
+            blade_width: BLADE_WIDTH,
+        };
+        upload_belt::write_buffer(queue, &self.params_buffer, 0, bytemuck::bytes_of(&params));
+        let uniforms = Uniforms {
+            view_proj,
+            field_size: FIELD_SIZE,
+            alpha_test: u32::from(self.coverage == CoverageMode::AlphaTest),
+            _padding: [0; 2],
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+    }
+
+    /// Generate the blades and draw them into `target`, a view of a
+    /// texture of the given size, resolving the multisampled image
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        if self.size != (width, height) {
+            (self.msaa_view, self.depth_view) =
+                create_targets(device, self.color_format, width, height);
+            self.size = (width, height);
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Grass Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Grass Generate Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.generate_pipeline);
+            compute_pass.set_bind_group(0, &self.generate_bind_group, &[]);
+            tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+            compute_pass.dispatch_workgroups(self.blade_count.div_ceil(64), 1, 1);
+        }
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Grass Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.msaa_view,
+                    resolve_target: Some(target),
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Discard,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+            render_pass.set_pipeline(&self.ground_pipeline);
+            render_pass.draw(0..4, 0..1);
+
+            render_pass.set_pipeline(match self.coverage {
+                CoverageMode::AlphaToCoverage => &self.coverage_pipeline,
+                CoverageMode::AlphaTest => &self.alpha_test_pipeline,
+            });
+            // One triangle strip per blade, the instance index picks the blade
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..VERTICES_PER_BLADE, 0..self.blade_count);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+}
+
+/// Multisampled color and depth attachments
+fn create_targets(
+    device: &wgpu::Device,
+    color_format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+) -> (wgpu::TextureView, wgpu::TextureView) {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    let create = |label, format| {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: SAMPLE_COUNT,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    };
+    (
+        create("Grass MSAA Color Texture", color_format),
+        create("Grass Depth Texture", DEPTH_FORMAT),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        validate(GRASS_GENERATE_SHADER);
+        validate(GRASS_RENDER_SHADER);
+        let vertices = format!("const VERTICES_PER_BLADE: u32 = {}u;", VERTICES_PER_BLADE);
+        assert!(GRASS_GENERATE_SHADER.contains(&vertices));
+        assert!(GRASS_RENDER_SHADER.contains(&vertices));
+        assert!(GRASS_GENERATE_SHADER.contains(&format!("const SEGMENTS: u32 = {}u;", SEGMENTS)));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<GrassVertex>(), 32);
+        assert_eq!(std::mem::size_of::<GenerateParams>(), 32);
+        assert_eq!(std::mem::size_of::<Uniforms>(), 80);
+        assert_eq!(VERTICES_PER_BLADE, 2 * (SEGMENTS + 1));
+    }
+
+    #[test]
+    fn test_vertex_buffer_fits_a_storage_binding() {
+        let size = MAX_BLADES as u64 * VERTICES_PER_BLADE as u64 * 32;
+        assert!(size <= wgpu::Limits::default().max_storage_buffer_binding_size);
+    }
+
+    #[test]
+    fn test_coverage_mode_labels() {
+        assert_ne!(
+            CoverageMode::AlphaToCoverage.label(),
+            CoverageMode::AlphaTest.label()
+        );
+    }
+}
@@ -0,0 +1,1157 @@
+//! Hardware ray tracing through wgpu's experimental ray queries
+//!
+//! GPUs with ray tracing units traverse acceleration structures in
+//! hardware: a bottom level structure (BLAS) per triangle mesh and a top
+//! level structure (TLAS) placing instances of those meshes in the world.
+//! [`AccelerationStructureBuilder`] collects meshes and instances on the CPU
+//! and builds both on the GPU, and any shader stage can then trace rays
+//! through the TLAS with a `ray_query`.
+//!
+//! Ray queries are behind [`wgpu::Features::EXPERIMENTAL_RAY_QUERY`], which
+//! only Vulkan, DirectX 12 and Metal adapters with ray tracing support
+//! expose, and which a device has to opt into with
+//! [`wgpu::ExperimentalFeatures`]; see [`request_ray_queries`]. Everything
+//! here checks the device with [`check_support`] first, so on other
+//! hardware it fails with a message saying what's missing instead of a
+//! validation error.
+//!
+//! [`RayQueryScene`] is the example: a handful of instanced meshes shaded
+//! in a fragment shader that casts one camera ray and one shadow ray per
+//! pixel, with the TLAS rebuilt every frame as the instances move.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::instanced_cubes::cube_mesh;
+use crate::lod::icosphere;
+use crate::math_utils::{
+    mat4_inverse, mat4_mul, rotation_y, scaling, translation, Mat4, MAT4_IDENTITY,
+};
+use crate::upload_belt;
+use wgpu::util::DeviceExt;
+
+/// Features a device needs for everything in this module
+pub const RAY_QUERY_FEATURES: wgpu::Features = wgpu::Features::EXPERIMENTAL_RAY_QUERY;
+
+/// Largest value an instance's `custom_data` can hold
+pub const MAX_CUSTOM_DATA: u32 = (1 << 24) - 1;
+
+/// Bytes between vertices in the shared vertex buffer
+const VERTEX_STRIDE: u64 = std::mem::size_of::<[f32; 3]>() as u64;
+
+pub const RAY_QUERY_SHADER: &str = r#"// Hardware Ray Queries Example
+// Every pixel traces a camera ray through the TLAS, looks up the normal of
+// the triangle it hit and traces a second ray towards the sun for the shadow
+enable wgpu_ray_query;
+
+struct Uniforms {
+    inverse_view_proj: mat4x4<f32>,
+    eye: vec3<f32>,
+    shadows: u32,
+    sun_direction: vec3<f32>,
+    // 0 shaded, 1 normals, 2 instances
+    view: u32,
+}
+
+struct InstanceInfo {
+    color: vec3<f32>,
+    first_index: u32,
+    first_vertex: u32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var scene: acceleration_structure;
+// Tightly packed xyz, the layout the BLAS was built from
+@group(0) @binding(2) var<storage, read> positions: array<f32>;
+@group(0) @binding(3) var<storage, read> indices: array<u32>;
+@group(0) @binding(4) var<storage, read> instances: array<InstanceInfo>;
+
+const T_MIN: f32 = 0.001;
+const T_MAX: f32 = 1000.0;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return VertexOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+fn vertex_position(index: u32) -> vec3<f32> {
+    let i = index * 3u;
+    return vec3<f32>(positions[i], positions[i + 1u], positions[i + 2u]);
+}
+
+fn sky(direction: vec3<f32>) -> vec3<f32> {
+    let up = clamp(direction.y * 0.5 + 0.5, 0.0, 1.0);
+    return mix(vec3<f32>(0.8, 0.85, 0.9), vec3<f32>(0.35, 0.55, 0.9), up);
+}
+
+fn hue(index: u32) -> vec3<f32> {
+    let h = fract(f32(index) * 0.618034);
+    return clamp(abs(fract(h + vec3<f32>(0.0, 2.0, 1.0) / 3.0) * 6.0 - 3.0) - 1.0, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+// World space normal of the hit triangle, facing the ray
+fn hit_normal(hit: RayIntersection, direction: vec3<f32>) -> vec3<f32> {
+    let info = instances[hit.instance_index];
+    let first = info.first_index + hit.primitive_index * 3u;
+    let a = vertex_position(info.first_vertex + indices[first]);
+    let b = vertex_position(info.first_vertex + indices[first + 1u]);
+    let c = vertex_position(info.first_vertex + indices[first + 2u]);
+    let object_normal = cross(b - a, c - a);
+    // Normals transform by the inverse transpose of the object to world matrix
+    let to_object = hit.world_to_object;
+    let linear = mat3x3<f32>(to_object[0], to_object[1], to_object[2]);
+    let normal = normalize(object_normal * linear);
+    return select(normal, -normal, dot(normal, direction) > 0.0);
+}
+
+fn in_shadow(origin: vec3<f32>) -> bool {
+    var query: ray_query;
+    let flags = RAY_FLAG_TERMINATE_ON_FIRST_HIT | RAY_FLAG_FORCE_OPAQUE;
+    rayQueryInitialize(&query, scene, RayDesc(flags, 0xffu, T_MIN, T_MAX, origin, uniforms.sun_direction));
+    rayQueryProceed(&query);
+    return rayQueryGetCommittedIntersection(&query).kind != RAY_QUERY_INTERSECTION_NONE;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let far = uniforms.inverse_view_proj * vec4<f32>(in.ndc, 1.0, 1.0);
+    let origin = uniforms.eye;
+    let direction = normalize(far.xyz / far.w - origin);
+
+    var query: ray_query;
+    rayQueryInitialize(&query, scene, RayDesc(RAY_FLAG_FORCE_OPAQUE, 0xffu, T_MIN, T_MAX, origin, direction));
+    rayQueryProceed(&query);
+    let hit = rayQueryGetCommittedIntersection(&query);
+    if hit.kind == RAY_QUERY_INTERSECTION_NONE {
+        return vec4<f32>(sky(direction), 1.0);
+    }
+
+    let normal = hit_normal(hit, direction);
+    if uniforms.view == 1u {
+        return vec4<f32>(normal * 0.5 + 0.5, 1.0);
+    }
+    if uniforms.view == 2u {
+        return vec4<f32>(hue(hit.instance_index), 1.0);
+    }
+
+    let point = origin + direction * hit.t;
+    var light = max(dot(normal, uniforms.sun_direction), 0.0);
+    if uniforms.shadows != 0u && light > 0.0 && in_shadow(point + normal * 0.001) {
+        light = 0.0;
+    }
+    let color = instances[hit.instance_index].color;
+    let ambient = sky(normal) * 0.25;
+    return vec4<f32>(color * (ambient + vec3<f32>(light)), 1.0);
+}
+"#;
+
+/// Why acceleration structures can't be built or traced
+#[derive(Debug, Clone, PartialEq)]
+pub enum HardwareRayTracingError {
+    /// The device was created without [`RAY_QUERY_FEATURES`] or the
+    /// acceleration structure limits
+    Unsupported {
+        adapter: String,
+        backend: wgpu::Backend,
+    },
+    /// There are no instances to put in the TLAS
+    EmptyScene,
+    /// A mesh's index count isn't a multiple of three
+    IncompleteTriangle { mesh: usize, index_count: usize },
+    /// A mesh's index points past its vertices
+    IndexOutOfRange {
+        mesh: usize,
+        index: u32,
+        vertex_count: usize,
+    },
+    /// An instance refers to a mesh of another builder
+    UnknownMesh { instance: usize },
+    /// An instance's `custom_data` doesn't fit in 24 bits
+    CustomDataTooLarge { instance: usize, custom_data: u32 },
+}
+
+impl std::fmt::Display for HardwareRayTracingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HardwareRayTracingError::Unsupported { adapter, backend } => write!(
+                f,
+                "Hardware ray tracing is not available on {} ({:?}): the device has no \
+                 EXPERIMENTAL_RAY_QUERY feature. It needs a GPU with ray tracing support \
+                 on Vulkan, DirectX 12 or Metal; the Compute Ray Tracer example runs on \
+                 any GPU instead",
+                adapter, backend
+            ),
+            HardwareRayTracingError::EmptyScene => write!(f, "The scene has no instances"),
+            HardwareRayTracingError::IncompleteTriangle { mesh, index_count } => write!(
+                f,
+                "Mesh {} has {} indices, which is not a whole number of triangles",
+                mesh, index_count
+            ),
+            HardwareRayTracingError::IndexOutOfRange {
+                mesh,
+                index,
+                vertex_count,
+            } => write!(
+                f,
+                "Mesh {} uses vertex {} but has only {} vertices",
+                mesh, index, vertex_count
+            ),
+            HardwareRayTracingError::UnknownMesh { instance } => {
+                write!(
+                    f,
+                    "Instance {} refers to a mesh that was never added",
+                    instance
+                )
+            }
+            HardwareRayTracingError::CustomDataTooLarge {
+                instance,
+                custom_data,
+            } => write!(
+                f,
+                "Instance {} has custom data {:#x}, more than the 24 bits a TLAS keeps",
+                instance, custom_data
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HardwareRayTracingError {}
+
+/// Whether `adapter` can trace rays in hardware
+pub fn adapter_supports_ray_queries(adapter: &wgpu::Adapter) -> bool {
+    adapter.features().contains(RAY_QUERY_FEATURES)
+}
+
+/// Opt `descriptor` into ray queries when `adapter` supports them
+///
+/// Ray queries are an experimental feature, so besides the feature flag the
+/// descriptor has to acknowledge [`wgpu::ExperimentalFeatures`], and the
+/// acceleration structure limits, all zero by default, are raised to the
+/// adapter's. Devices on other adapters are left as they are.
+pub fn request_ray_queries(adapter: &wgpu::Adapter, descriptor: &mut wgpu::DeviceDescriptor) {
+    if !adapter_supports_ray_queries(adapter) {
+        return;
+    }
+    descriptor.required_features |= RAY_QUERY_FEATURES;
+    descriptor.required_limits = descriptor
+        .required_limits
+        .clone()
+        .using_acceleration_structure_values(adapter.limits());
+    // SAFETY: only the ray query API is used experimentally, through the
+    // validated entry points of this module
+    descriptor.experimental_features = unsafe { wgpu::ExperimentalFeatures::enabled() };
+}
+
+/// Check that `device` was created with ray queries
+pub fn check_support(device: &wgpu::Device) -> Result<(), HardwareRayTracingError> {
+    // A TLAS can't be bound without the limit, whatever the features say
+    if device.features().contains(RAY_QUERY_FEATURES)
+        && device.limits().max_acceleration_structures_per_shader_stage > 0
+    {
+        return Ok(());
+    }
+    let info = device.adapter_info();
+    Err(HardwareRayTracingError::Unsupported {
+        adapter: info.name,
+        backend: info.backend,
+    })
+}
+
+/// Handle to a mesh added to an [`AccelerationStructureBuilder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MeshId(usize);
+
+impl MeshId {
+    /// Position of the mesh in [`AccelerationStructures::meshes`]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Where a mesh lives in the shared vertex and index buffers
+///
+/// Indices are relative to `first_vertex`, the way the BLAS reads them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshRange {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+    pub first_index: u32,
+    pub index_count: u32,
+}
+
+/// One placement of a mesh in the TLAS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instance {
+    pub mesh: MeshId,
+    /// Object to world transform; only the affine part is used
+    pub transform: Mat4,
+    /// Read by shaders as `instance_custom_data`, at most [`MAX_CUSTOM_DATA`]
+    pub custom_data: u32,
+    /// Rays skip the instance unless their cull mask shares a bit with it
+    pub mask: u8,
+}
+
+impl Instance {
+    /// Visible to every ray, with no custom data
+    pub fn new(mesh: MeshId, transform: Mat4) -> Self {
+        Self {
+            mesh,
+            transform,
+            custom_data: 0,
+            mask: 0xff,
+        }
+    }
+}
+
+/// The row-major 3x4 matrix a TLAS instance takes, from a column-major `Mat4`
+pub fn tlas_transform(transform: &Mat4) -> [f32; 12] {
+    std::array::from_fn(|i| transform[i % 4][i / 4])
+}
+
+/// Collects triangle meshes and their instances for hardware ray tracing
+///
+/// # Examples
+///
+/// ```no_run
+/// use wgpu_playground_core::hardware_ray_tracing::{AccelerationStructureBuilder, Instance};
+/// use wgpu_playground_core::math_utils::translation;
+/// # fn example(device: &wgpu::Device, queue: &wgpu::Queue) {
+/// let mut builder = AccelerationStructureBuilder::new().with_label("Triangle");
+/// let triangle = builder.add_mesh(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], &[0, 1, 2]);
+/// builder.add_instance(Instance::new(triangle, translation([0.0, 0.0, -2.0])));
+/// match builder.build(device, queue) {
+///     Ok(structures) => { /* bind structures.tlas() as an acceleration_structure */ }
+///     Err(e) => eprintln!("{}", e),
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AccelerationStructureBuilder {
+    positions: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+    meshes: Vec<MeshRange>,
+    instances: Vec<Instance>,
+    label: Option<String>,
+}
+
+impl AccelerationStructureBuilder {
+    /// An empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the label the buffers and structures are created with
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Add a mesh of counter-clockwise triangles, one BLAS each
+    pub fn add_mesh(&mut self, positions: &[[f32; 3]], indices: &[u32]) -> MeshId {
+        self.meshes.push(MeshRange {
+            first_vertex: self.positions.len() as u32,
+            vertex_count: positions.len() as u32,
+            first_index: self.indices.len() as u32,
+            index_count: indices.len() as u32,
+        });
+        self.positions.extend_from_slice(positions);
+        self.indices.extend_from_slice(indices);
+        MeshId(self.meshes.len() - 1)
+    }
+
+    /// Place a mesh in the scene, returning the instance's index
+    pub fn add_instance(&mut self, instance: Instance) -> usize {
+        self.instances.push(instance);
+        self.instances.len() - 1
+    }
+
+    pub fn meshes(&self) -> &[MeshRange] {
+        &self.meshes
+    }
+
+    pub fn instances(&self) -> &[Instance] {
+        &self.instances
+    }
+
+    /// Check the meshes and instances without touching the GPU
+    pub fn validate(&self) -> Result<(), HardwareRayTracingError> {
+        if self.instances.is_empty() {
+            return Err(HardwareRayTracingError::EmptyScene);
+        }
+        for (mesh, range) in self.meshes.iter().enumerate() {
+            if range.index_count % 3 != 0 {
+                return Err(HardwareRayTracingError::IncompleteTriangle {
+                    mesh,
+                    index_count: range.index_count as usize,
+                });
+            }
+            let first = range.first_index as usize;
+            let indices = &self.indices[first..first + range.index_count as usize];
+            if let Some(&index) = indices.iter().find(|&&i| i >= range.vertex_count) {
+                return Err(HardwareRayTracingError::IndexOutOfRange {
+                    mesh,
+                    index,
+                    vertex_count: range.vertex_count as usize,
+                });
+            }
+        }
+        for (instance, placed) in self.instances.iter().enumerate() {
+            if placed.mesh.0 >= self.meshes.len() {
+                return Err(HardwareRayTracingError::UnknownMesh { instance });
+            }
+            if placed.custom_data > MAX_CUSTOM_DATA {
+                return Err(HardwareRayTracingError::CustomDataTooLarge {
+                    instance,
+                    custom_data: placed.custom_data,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Upload the meshes, build a BLAS per mesh and the TLAS over the
+    /// instances, and submit the build
+    pub fn build(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<AccelerationStructures, HardwareRayTracingError> {
+        check_support(device)?;
+        self.validate()?;
+        let label = self.label.as_deref().unwrap_or("Acceleration Structure");
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        // Shaders usually need the triangles too, for normals and the like
+        let usage = wgpu::BufferUsages::BLAS_INPUT | wgpu::BufferUsages::STORAGE;
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} Vertices", label)),
+            contents: bytemuck::cast_slice(&self.positions),
+            usage,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} Indices", label)),
+            contents: bytemuck::cast_slice(&self.indices),
+            usage,
+        });
+
+        let sizes: Vec<_> = self
+            .meshes
+            .iter()
+            .map(|range| wgpu::BlasTriangleGeometrySizeDescriptor {
+                vertex_format: wgpu::VertexFormat::Float32x3,
+                vertex_count: range.vertex_count,
+                index_format: Some(wgpu::IndexFormat::Uint32),
+                index_count: Some(range.index_count),
+                flags: wgpu::AccelerationStructureGeometryFlags::OPAQUE,
+            })
+            .collect();
+        tracker.record(ApiCategory::Device, "create_blas");
+        let blases: Vec<_> = sizes
+            .iter()
+            .enumerate()
+            .map(|(mesh, size)| {
+                device.create_blas(
+                    &wgpu::CreateBlasDescriptor {
+                        label: Some(&format!("{} BLAS {}", label, mesh)),
+                        flags: wgpu::AccelerationStructureFlags::PREFER_FAST_TRACE,
+                        update_mode: wgpu::AccelerationStructureUpdateMode::Build,
+                    },
+                    wgpu::BlasGeometrySizeDescriptors::Triangles {
+                        descriptors: vec![size.clone()],
+                    },
+                )
+            })
+            .collect();
+
+        tracker.record(ApiCategory::Device, "create_tlas");
+        let tlas = device.create_tlas(&wgpu::CreateTlasDescriptor {
+            label: Some(&format!("{} TLAS", label)),
+            max_instances: self.instances.len() as u32,
+            flags: wgpu::AccelerationStructureFlags::PREFER_FAST_TRACE,
+            update_mode: wgpu::AccelerationStructureUpdateMode::Build,
+        });
+
+        let mut structures = AccelerationStructures {
+            vertex_buffer,
+            index_buffer,
+            meshes: self.meshes.clone(),
+            blases,
+            tlas,
+            instances: self.instances.clone(),
+        };
+        structures.write_instances();
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some(&format!("{} Build Encoder", label)),
+        });
+        let entries: Vec<_> = structures
+            .blases
+            .iter()
+            .zip(&sizes)
+            .zip(&structures.meshes)
+            .map(|((blas, size), range)| wgpu::BlasBuildEntry {
+                blas,
+                geometry: wgpu::BlasGeometries::TriangleGeometries(vec![
+                    wgpu::BlasTriangleGeometry {
+                        size,
+                        vertex_buffer: &structures.vertex_buffer,
+                        first_vertex: range.first_vertex,
+                        vertex_stride: VERTEX_STRIDE,
+                        index_buffer: Some(&structures.index_buffer),
+                        first_index: Some(range.first_index),
+                        transform_buffer: None,
+                        transform_buffer_offset: None,
+                    },
+                ]),
+            })
+            .collect();
+        tracker.record(ApiCategory::CommandEncoder, "build_acceleration_structures");
+        encoder.build_acceleration_structures(&entries, std::iter::once(&structures.tlas));
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(std::iter::once(encoder.finish()));
+
+        Ok(structures)
+    }
+}
+
+/// The GPU side of an [`AccelerationStructureBuilder`]
+pub struct AccelerationStructures {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    meshes: Vec<MeshRange>,
+    blases: Vec<wgpu::Blas>,
+    tlas: wgpu::Tlas,
+    instances: Vec<Instance>,
+}
+
+impl AccelerationStructures {
+    /// The TLAS to bind as an `acceleration_structure`
+    pub fn tlas(&self) -> &wgpu::Tlas {
+        &self.tlas
+    }
+
+    /// Positions of every mesh as tightly packed `f32` triples
+    pub fn vertex_buffer(&self) -> &wgpu::Buffer {
+        &self.vertex_buffer
+    }
+
+    /// `u32` indices of every mesh, relative to the mesh's first vertex
+    pub fn index_buffer(&self) -> &wgpu::Buffer {
+        &self.index_buffer
+    }
+
+    pub fn meshes(&self) -> &[MeshRange] {
+        &self.meshes
+    }
+
+    pub fn instances(&self) -> &[Instance] {
+        &self.instances
+    }
+
+    pub fn triangle_count(&self) -> u32 {
+        self.meshes.iter().map(|range| range.index_count / 3).sum()
+    }
+
+    /// Move an instance; takes effect with the next [`Self::rebuild_tlas`]
+    pub fn set_transform(&mut self, instance: usize, transform: Mat4) {
+        self.instances[instance].transform = transform;
+    }
+
+    /// Record a rebuild of the TLAS from the current instances; the BLASes
+    /// stay as they are
+    pub fn rebuild_tlas(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        self.write_instances();
+        ApiCoverageTracker::global()
+            .record(ApiCategory::CommandEncoder, "build_acceleration_structures");
+        encoder.build_acceleration_structures(std::iter::empty(), std::iter::once(&self.tlas));
+    }
+
+    fn write_instances(&mut self) {
+        for (index, instance) in self.instances.iter().enumerate() {
+            self.tlas[index] = Some(wgpu::TlasInstance::new(
+                &self.blases[instance.mesh.0],
+                tlas_transform(&instance.transform),
+                instance.custom_data,
+                instance.mask,
+            ));
+        }
+    }
+}
+
+/// What the example draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RayQueryView {
+    #[default]
+    Shaded,
+    /// World space normals of the hit triangles
+    Normals,
+    /// A color per TLAS instance
+    Instances,
+}
+
+impl RayQueryView {
+    pub const ALL: [RayQueryView; 3] = [
+        RayQueryView::Shaded,
+        RayQueryView::Normals,
+        RayQueryView::Instances,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RayQueryView::Shaded => "Shaded",
+            RayQueryView::Normals => "Normals",
+            RayQueryView::Instances => "Instances",
+        }
+    }
+}
+
+/// Settings of the ray query example
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayQuerySettings {
+    /// Trace a second ray per pixel towards the sun
+    pub shadows: bool,
+    /// Move the instances, rebuilding the TLAS every frame
+    pub animate: bool,
+    /// Degrees above the horizon
+    pub sun_elevation: f32,
+    pub view: RayQueryView,
+}
+
+impl Default for RayQuerySettings {
+    fn default() -> Self {
+        Self {
+            shadows: true,
+            animate: true,
+            sun_elevation: 40.0,
+            view: RayQueryView::Shaded,
+        }
+    }
+}
+
+impl RayQuerySettings {
+    /// Unit vector towards the sun
+    pub fn sun_direction(&self) -> [f32; 3] {
+        let elevation = self.sun_elevation.to_radians();
+        let azimuth = 35f32.to_radians();
+        [
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+            elevation.cos() * azimuth.cos(),
+        ]
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    inverse_view_proj: Mat4,
+    eye: [f32; 3],
+    shadows: u32,
+    sun_direction: [f32; 3],
+    view: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct InstanceInfo {
+    color: [f32; 3],
+    first_index: u32,
+    first_vertex: u32,
+    _padding: [u32; 3],
+}
+
+/// Cubes circling the middle of the scene
+const ORBITING_CUBES: usize = 6;
+
+/// Instances of the example scene: the ground, a large sphere in the
+/// middle, the orbiting cubes and a row of small spheres
+struct SceneLayout {
+    ground: MeshId,
+    cube: MeshId,
+    sphere: MeshId,
+}
+
+impl SceneLayout {
+    fn new(builder: &mut AccelerationStructureBuilder) -> Self {
+        let ground = builder.add_mesh(
+            &[
+                [-1.0, 0.0, -1.0],
+                [-1.0, 0.0, 1.0],
+                [1.0, 0.0, 1.0],
+                [1.0, 0.0, -1.0],
+            ],
+            &[0, 1, 2, 2, 3, 0],
+        );
+        let (cube_vertices, cube_indices) = cube_mesh();
+        let cube = builder.add_mesh(
+            &cube_vertices.iter().map(|v| v.position).collect::<Vec<_>>(),
+            &cube_indices
+                .iter()
+                .map(|&i| u32::from(i))
+                .collect::<Vec<_>>(),
+        );
+        let (sphere_positions, sphere_indices) = icosphere(3);
+        let sphere = builder.add_mesh(
+            &sphere_positions,
+            &sphere_indices
+                .iter()
+                .map(|&i| u32::from(i))
+                .collect::<Vec<_>>(),
+        );
+        Self {
+            ground,
+            cube,
+            sphere,
+        }
+    }
+
+    /// Every instance with its color at `time` seconds
+    fn instances(&self, time: f32) -> Vec<(Instance, [f32; 3])> {
+        let mut instances = vec![
+            (
+                Instance::new(self.ground, scaling([12.0, 1.0, 12.0])),
+                [0.7, 0.7, 0.7],
+            ),
+            (
+                Instance::new(
+                    self.sphere,
+                    mat4_mul(&translation([0.0, 1.2, 0.0]), &scaling([1.2; 3])),
+                ),
+                [0.9, 0.45, 0.2],
+            ),
+        ];
+        for i in 0..ORBITING_CUBES {
+            let angle = time * 0.5 + i as f32 / ORBITING_CUBES as f32 * std::f32::consts::TAU;
+            let transform = mat4_mul(
+                &rotation_y(angle),
+                &mat4_mul(
+                    &translation([3.2, 0.6 + 0.3 * (time * 2.0 + i as f32).sin().abs(), 0.0]),
+                    &rotation_y(time * 1.5 + i as f32),
+                ),
+            );
+            let color = if i % 2 == 0 {
+                [0.2, 0.5, 0.9]
+            } else {
+                [0.3, 0.8, 0.4]
+            };
+            instances.push((Instance::new(self.cube, transform), color));
+        }
+        for i in 0..5 {
+            let x = (i as f32 - 2.0) * 1.6;
+            let transform = mat4_mul(&translation([x, 0.4, -5.0]), &scaling([0.4; 3]));
+            instances.push((Instance::new(self.sphere, transform), [0.85, 0.85, 0.3]));
+        }
+        for (index, (instance, _)) in instances.iter_mut().enumerate() {
+            instance.custom_data = index as u32;
+        }
+        instances
+    }
+}
+
+/// The hardware ray query example
+pub struct RayQueryScene {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    structures: AccelerationStructures,
+    layout: SceneLayout,
+    inverse_view_proj: Mat4,
+    eye: [f32; 3],
+    time: f32,
+    /// Time the TLAS was last built for
+    built_for: Option<f32>,
+    tlas_builds: u64,
+    pub settings: RayQuerySettings,
+}
+
+impl RayQueryScene {
+    /// Build the scene's acceleration structures and the pipeline drawing
+    /// into `color_format` targets
+    ///
+    /// Fails with [`HardwareRayTracingError::Unsupported`] on devices
+    /// without ray queries.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+    ) -> Result<Self, HardwareRayTracingError> {
+        check_support(device)?;
+
+        let mut builder = AccelerationStructureBuilder::new().with_label("Ray Query Scene");
+        let layout = SceneLayout::new(&mut builder);
+        let instances = layout.instances(0.0);
+        for &(instance, _) in &instances {
+            builder.add_instance(instance);
+        }
+        let structures = builder.build(device, queue)?;
+
+        let infos: Vec<InstanceInfo> = instances
+            .iter()
+            .map(|(instance, color)| {
+                let range = structures.meshes()[instance.mesh.index()];
+                InstanceInfo {
+                    color: *color,
+                    first_index: range.first_index,
+                    first_vertex: range.first_vertex,
+                    _padding: [0; 3],
+                }
+            })
+            .collect();
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Ray Query Shader"),
+            source: wgpu::ShaderSource::Wgsl(RAY_QUERY_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Ray Query Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Ray Query Instance Buffer"),
+            contents: bytemuck::cast_slice(&infos),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let storage_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Ray Query Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::AccelerationStructure {
+                        vertex_return: false,
+                    },
+                    count: None,
+                },
+                storage_entry(2),
+                storage_entry(3),
+                storage_entry(4),
+            ],
+        });
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Ray Query Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: structures.tlas().as_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: structures.vertex_buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: structures.index_buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: instance_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Ray Query Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Ray Query Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Ok(Self {
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            structures,
+            layout,
+            inverse_view_proj: MAT4_IDENTITY,
+            eye: [0.0; 3],
+            time: 0.0,
+            built_for: Some(0.0),
+            tlas_builds: 1,
+            settings: RayQuerySettings::default(),
+        })
+    }
+
+    pub fn structures(&self) -> &AccelerationStructures {
+        &self.structures
+    }
+
+    /// TLAS builds so far, including the first one
+    pub fn tlas_builds(&self) -> u64 {
+        self.tlas_builds
+    }
+
+    /// Set the camera, and advance the animation by `delta_time` seconds
+    /// if it is running
+    pub fn update(&mut self, view_proj: Mat4, eye: [f32; 3], delta_time: f32) {
+        self.inverse_view_proj = mat4_inverse(&view_proj).unwrap_or(MAT4_IDENTITY);
+        self.eye = eye;
+        if self.settings.animate {
+            self.time += delta_time;
+        }
+    }
+
+    /// Rebuild the TLAS if the instances moved, then draw into `target`
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+    ) {
+        let uniforms = Uniforms {
+            inverse_view_proj: self.inverse_view_proj,
+            eye: self.eye,
+            shadows: self.settings.shadows as u32,
+            sun_direction: self.settings.sun_direction(),
+            view: self.settings.view as u32,
+        };
+        let tracker = ApiCoverageTracker::global();
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Ray Query Encoder"),
+        });
+        if self.built_for != Some(self.time) {
+            for (index, (instance, _)) in self.layout.instances(self.time).iter().enumerate() {
+                self.structures.set_transform(index, instance.transform);
+            }
+            self.structures.rebuild_tlas(&mut encoder);
+            self.built_for = Some(self.time);
+            self.tlas_builds += 1;
+        }
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Ray Query Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            tracker.record(ApiCategory::RenderPass, "set_pipeline");
+            pass.set_pipeline(&self.pipeline);
+            tracker.record(ApiCategory::RenderPass, "set_bind_group");
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "draw");
+            pass.draw(0..3, 0..1);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle_builder() -> (AccelerationStructureBuilder, MeshId) {
+        let mut builder = AccelerationStructureBuilder::new();
+        let mesh = builder.add_mesh(&[[0.0; 3], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], &[0, 1, 2]);
+        (builder, mesh)
+    }
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(RAY_QUERY_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::RAY_QUERY,
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<Uniforms>(), 96);
+        assert_eq!(std::mem::size_of::<InstanceInfo>(), 32);
+    }
+
+    #[test]
+    fn test_meshes_share_buffers() {
+        let (mut builder, first) = triangle_builder();
+        let second = builder.add_mesh(
+            &[[0.0; 3], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+            &[0, 1, 2, 2, 3, 0],
+        );
+        assert_eq!((first.index(), second.index()), (0, 1));
+        assert_eq!(
+            builder.meshes()[1],
+            MeshRange {
+                first_vertex: 3,
+                vertex_count: 4,
+                first_index: 3,
+                index_count: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let (mut builder, mesh) = triangle_builder();
+        assert_eq!(builder.validate(), Err(HardwareRayTracingError::EmptyScene));
+
+        builder.add_instance(Instance::new(mesh, MAT4_IDENTITY));
+        assert_eq!(builder.validate(), Ok(()));
+
+        let mut too_large = Instance::new(mesh, MAT4_IDENTITY);
+        too_large.custom_data = MAX_CUSTOM_DATA + 1;
+        let mut invalid = builder.clone();
+        invalid.add_instance(too_large);
+        assert!(matches!(
+            invalid.validate(),
+            Err(HardwareRayTracingError::CustomDataTooLarge { instance: 1, .. })
+        ));
+
+        let mut invalid = builder.clone();
+        invalid.add_instance(Instance::new(MeshId(7), MAT4_IDENTITY));
+        assert_eq!(
+            invalid.validate(),
+            Err(HardwareRayTracingError::UnknownMesh { instance: 1 })
+        );
+
+        let mut invalid = builder.clone();
+        invalid.add_mesh(&[[0.0; 3]; 3], &[0, 1]);
+        assert!(matches!(
+            invalid.validate(),
+            Err(HardwareRayTracingError::IncompleteTriangle { mesh: 1, .. })
+        ));
+
+        let mut invalid = builder;
+        invalid.add_mesh(&[[0.0; 3]; 3], &[0, 1, 3]);
+        assert_eq!(
+            invalid.validate(),
+            Err(HardwareRayTracingError::IndexOutOfRange {
+                mesh: 1,
+                index: 3,
+                vertex_count: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_tlas_transform_is_row_major() {
+        let transform = mat4_mul(&translation([1.0, 2.0, 3.0]), &scaling([4.0, 5.0, 6.0]));
+        assert_eq!(
+            tlas_transform(&transform),
+            [4.0, 0.0, 0.0, 1.0, 0.0, 5.0, 0.0, 2.0, 0.0, 0.0, 6.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn test_scene_instances() {
+        let mut builder = AccelerationStructureBuilder::new();
+        let layout = SceneLayout::new(&mut builder);
+        let start = layout.instances(0.0);
+        assert_eq!(start.len(), 2 + ORBITING_CUBES + 5);
+        assert!(start
+            .iter()
+            .enumerate()
+            .all(|(index, (instance, _))| instance.custom_data == index as u32));
+        for &(instance, _) in &start {
+            builder.add_instance(instance);
+        }
+        assert_eq!(builder.validate(), Ok(()));
+
+        // Only the cubes move
+        let later = layout.instances(1.0);
+        let moved: Vec<_> = start
+            .iter()
+            .zip(&later)
+            .map(|((a, _), (b, _))| a.transform != b.transform)
+            .collect();
+        assert_eq!(moved.iter().filter(|&&m| m).count(), ORBITING_CUBES);
+    }
+
+    #[test]
+    fn test_unsupported_message_names_the_fallback() {
+        let error = HardwareRayTracingError::Unsupported {
+            adapter: "llvmpipe".to_string(),
+            backend: wgpu::Backend::Gl,
+        };
+        let message = error.to_string();
+        assert!(message.contains("llvmpipe (Gl)"));
+        assert!(message.contains("EXPERIMENTAL_RAY_QUERY"));
+        assert!(message.contains("Compute Ray Tracer"));
+    }
+}
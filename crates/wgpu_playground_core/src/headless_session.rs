@@ -0,0 +1,554 @@
+//! Headless rendering of saved projects
+//!
+//! A [`Session`] owns a headless device and the shader of the loaded
+//! project. It backs the C API, the Python bindings, the render service and
+//! `wgpu_playground watch`, which only translate their inputs and errors.
+
+use std::fmt;
+use std::path::Path;
+
+use naga::{Binding, Module, ShaderStage};
+
+use crate::adapter::{self, AdapterOptions};
+use crate::shader_benchmark::{self, PipelineTarget};
+use crate::shader_translation;
+use crate::state::PlaygroundState;
+use crate::visual_regression;
+
+/// Format of frames returned by [`Session::render_frame`]
+pub const FRAME_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Errors from a headless session
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionError {
+    /// A pointer, string or size passed by the caller is invalid
+    InvalidArgument(String),
+    /// No GPU adapter or device could be created
+    NoAdapter(String),
+    /// The project file could not be read or parsed
+    Project(String),
+    /// The shader is missing, invalid or lacks the needed entry points
+    Shader(String),
+    /// The device rejected a pipeline, dispatch or readback
+    Gpu(String),
+    /// The caller's output buffer is too small
+    BufferTooSmall { required: usize, provided: usize },
+    /// The visual regression reference could not be used
+    Reference(String),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::InvalidArgument(msg) => write!(f, "Invalid argument: {}", msg),
+            SessionError::NoAdapter(msg) => write!(f, "No GPU available: {}", msg),
+            SessionError::Project(msg) => write!(f, "Project error: {}", msg),
+            SessionError::Shader(msg) => write!(f, "Shader error: {}", msg),
+            SessionError::Gpu(msg) => write!(f, "GPU error: {}", msg),
+            SessionError::BufferTooSmall { required, provided } => write!(
+                f,
+                "Output buffer too small: {} bytes required, {} provided",
+                required, provided
+            ),
+            SessionError::Reference(msg) => write!(f, "Reference error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// Headless device plus the loaded project
+pub struct Session {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    adapter_info: wgpu::AdapterInfo,
+    project: PlaygroundState,
+    shader_source: Option<String>,
+}
+
+impl Session {
+    /// Create a session on the given backends
+    ///
+    /// Falls back to a software adapter if no hardware adapter is found.
+    pub fn new(backends: wgpu::Backends) -> Result<Self, SessionError> {
+        let instance = adapter::create_instance(backends);
+        let options = AdapterOptions::default().with_backends(backends);
+        let adapter = pollster::block_on(adapter::request_adapter(&instance, &options, None))
+            .or_else(|_| {
+                pollster::block_on(adapter::request_adapter(
+                    &instance,
+                    &options.clone().with_fallback_adapter(true),
+                    None,
+                ))
+            })
+            .map_err(|e| SessionError::NoAdapter(e.to_string()))?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+            label: Some("Headless Session Device"),
+            ..Default::default()
+        }))
+        .map_err(|e| SessionError::NoAdapter(e.to_string()))?;
+
+        Ok(Self {
+            device,
+            queue,
+            adapter_info: adapter.get_info(),
+            project: PlaygroundState::default(),
+            shader_source: None,
+        })
+    }
+
+    /// Information about the adapter the session runs on
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.adapter_info
+    }
+
+    /// Load a project saved by the playground
+    ///
+    /// The project's shader editor source becomes the session shader.
+    pub fn load_project(&mut self, path: &Path) -> Result<(), SessionError> {
+        let project = PlaygroundState::load_from_file(path)
+            .map_err(|e| SessionError::Project(format!("{}: {}", path.display(), e)))?;
+        self.set_project(project);
+        Ok(())
+    }
+
+    /// Use an already parsed project
+    ///
+    /// Like [`Self::load_project`], the shader editor source becomes the
+    /// session shader.
+    pub fn set_project(&mut self, project: PlaygroundState) {
+        self.shader_source = project
+            .shader_editor
+            .as_ref()
+            .map(|editor| editor.source_code.clone())
+            .filter(|source| !source.trim().is_empty());
+        self.project = project;
+    }
+
+    /// Replace the session shader
+    pub fn set_shader(&mut self, source: &str) {
+        self.shader_source = Some(source.to_string());
+    }
+
+    /// The current shader source, if any
+    pub fn shader_source(&self) -> Option<&str> {
+        self.shader_source.as_deref()
+    }
+
+    fn shader(&self) -> Result<(&str, Module), SessionError> {
+        let source = self
+            .shader_source
+            .as_deref()
+            .ok_or_else(|| SessionError::Shader("No shader loaded".into()))?;
+        let (module, _) =
+            shader_translation::parse_and_validate(source).map_err(SessionError::Shader)?;
+        Ok((source, module))
+    }
+
+    /// Render the shader's vertex/fragment pair with `draw(0..3)`
+    ///
+    /// Entry points come from the project's render pipeline settings, or
+    /// the first vertex and fragment entry points of the shader. The vertex
+    /// stage must not take vertex buffer inputs. Returns tightly packed
+    /// `width * height` RGBA8 pixels in [`FRAME_FORMAT`].
+    pub fn render_frame(&self, width: u32, height: u32) -> Result<Vec<u8>, SessionError> {
+        if width == 0 || height == 0 {
+            return Err(SessionError::InvalidArgument(format!(
+                "Frame size {}x{} is empty",
+                width, height
+            )));
+        }
+        let (source, module) = self.shader()?;
+        let (vertex, fragment) = self.render_entry_points(&module)?;
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Session Frame"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FRAME_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let scope = self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = self.create_shader_module(source);
+        let pipeline = self
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Session Render Pipeline"),
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(&vertex),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(&fragment),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: FRAME_FORMAT,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Session Render Encoder"),
+            });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Session Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.draw(0..3, 0..1);
+        }
+        self.queue.submit(Some(encoder.finish()));
+        if let Some(error) = pollster::block_on(scope.pop()) {
+            return Err(SessionError::Gpu(error.to_string()));
+        }
+
+        let image = pollster::block_on(visual_regression::capture_texture(
+            &self.device,
+            &self.queue,
+            &texture,
+        ))
+        .map_err(|e| SessionError::Gpu(e.to_string()))?;
+        Ok(image.into_raw())
+    }
+
+    /// Run a compute entry point over a single storage buffer
+    ///
+    /// `input` is uploaded to a `read_write` storage buffer at
+    /// `@group(0) @binding(0)`, the entry point is dispatched with
+    /// `workgroups` and the first `output_len` bytes of the buffer are read
+    /// back. Without an explicit `entry_point`, the project's compute
+    /// pipeline setting or the shader's first compute entry point is used.
+    pub fn run_compute(
+        &self,
+        entry_point: Option<&str>,
+        input: &[u8],
+        output_len: usize,
+        workgroups: [u32; 3],
+    ) -> Result<Vec<u8>, SessionError> {
+        let (source, module) = self.shader()?;
+        let entry_point = match entry_point {
+            Some(name) => name.to_string(),
+            None => self.compute_entry_point(&module)?,
+        };
+        require_entry_point(&module, &entry_point, ShaderStage::Compute)?;
+
+        let size = input.len().max(output_len).max(4).next_multiple_of(4) as u64;
+        let storage = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Session Compute Storage"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Session Compute Readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        self.queue.write_buffer(&storage, 0, input);
+
+        let scope = self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = self.create_shader_module(source);
+        let pipeline = self
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Session Compute Pipeline"),
+                layout: None,
+                module: &shader,
+                entry_point: Some(&entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Session Compute Bind Group"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: storage.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Session Compute Encoder"),
+            });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Session Compute Pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            let [x, y, z] = workgroups;
+            pass.dispatch_workgroups(x, y, z);
+        }
+        encoder.copy_buffer_to_buffer(&storage, 0, &readback, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+        if let Some(error) = pollster::block_on(scope.pop()) {
+            return Err(SessionError::Gpu(error.to_string()));
+        }
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            sender.send(result).ok();
+        });
+        let _ = self.device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        });
+        receiver
+            .recv()
+            .map_err(|_| SessionError::Gpu("Readback was cancelled".into()))?
+            .map_err(|e| SessionError::Gpu(format!("Failed to map readback buffer: {}", e)))?;
+        let output = slice.get_mapped_range()[..output_len].to_vec();
+        readback.unmap();
+        Ok(output)
+    }
+
+    fn create_shader_module(&self, source: &str) -> wgpu::ShaderModule {
+        self.device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Session Shader"),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            })
+    }
+
+    fn render_entry_points(&self, module: &Module) -> Result<(String, String), SessionError> {
+        let configured = self.project.render_pipeline_panel.as_ref().map(|panel| {
+            (
+                panel.vertex_entry_point.clone(),
+                panel.fragment_entry_point.clone(),
+            )
+        });
+        let (vertex, fragment) = match configured {
+            Some((vertex, fragment)) if !vertex.is_empty() && !fragment.is_empty() => {
+                (vertex, fragment)
+            }
+            _ => match shader_benchmark::pipeline_target(module) {
+                Some(PipelineTarget::Render {
+                    vertex,
+                    fragment: Some(fragment),
+                }) => (vertex, fragment),
+                _ => {
+                    return Err(SessionError::Shader(
+                        "Shader needs a vertex and a fragment entry point".into(),
+                    ))
+                }
+            },
+        };
+
+        let vertex_ep = require_entry_point(module, &vertex, ShaderStage::Vertex)?;
+        require_entry_point(module, &fragment, ShaderStage::Fragment)?;
+        let takes_vertex_buffers = vertex_ep.function.arguments.iter().any(|arg| {
+            match &arg.binding {
+                Some(Binding::Location { .. }) => true,
+                Some(_) => false,
+                None => matches!(
+                    &module.types[arg.ty].inner,
+                    naga::TypeInner::Struct { members, .. }
+                        if members.iter().any(|m| matches!(m.binding, Some(Binding::Location { .. })))
+                ),
+            }
+        });
+        if takes_vertex_buffers {
+            return Err(SessionError::Shader(format!(
+                "Vertex entry point '{}' reads vertex buffers; only builtin inputs are supported",
+                vertex
+            )));
+        }
+        Ok((vertex, fragment))
+    }
+
+    fn compute_entry_point(&self, module: &Module) -> Result<String, SessionError> {
+        if let Some(panel) = &self.project.compute_pipeline_panel {
+            if !panel.entry_point.is_empty() {
+                return Ok(panel.entry_point.clone());
+            }
+        }
+        module
+            .entry_points
+            .iter()
+            .find(|ep| ep.stage == ShaderStage::Compute)
+            .map(|ep| ep.name.clone())
+            .ok_or_else(|| SessionError::Shader("Shader has no compute entry point".into()))
+    }
+}
+
+fn require_entry_point<'m>(
+    module: &'m Module,
+    name: &str,
+    stage: ShaderStage,
+) -> Result<&'m naga::EntryPoint, SessionError> {
+    module
+        .entry_points
+        .iter()
+        .find(|ep| ep.name == name && ep.stage == stage)
+        .ok_or_else(|| SessionError::Shader(format!("No {:?} entry point named '{}'", stage, name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE: &str = r#"
+@vertex
+fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+    let x = f32(i32(i) - 1);
+    let y = f32(i32(i & 1u) * 2 - 1);
+    return vec4<f32>(x * 3.0, y * 3.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0, 0.0, 0.0, 1.0);
+}
+"#;
+
+    const DOUBLE: &str = r#"
+@group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    data[id.x] = data[id.x] * 2u;
+}
+"#;
+
+    fn session() -> Option<Session> {
+        match Session::new(wgpu::Backends::all()) {
+            Ok(session) => Some(session),
+            Err(e) => {
+                eprintln!("Skipping test: {}", e);
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_display() {
+        let error = SessionError::BufferTooSmall {
+            required: 16,
+            provided: 4,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Output buffer too small: 16 bytes required, 4 provided"
+        );
+    }
+
+    #[test]
+    fn test_load_project_takes_shader_source() {
+        let Some(mut session) = session() else {
+            return;
+        };
+        let mut state = PlaygroundState::default();
+        state.shader_editor = Some(crate::state::ShaderEditorState {
+            source_code: TRIANGLE.to_string(),
+            label: "triangle".into(),
+            file_path: String::new(),
+            ..Default::default()
+        });
+        let path = std::env::temp_dir().join("wgpu_playground_session_project.json");
+        state.save_to_file(&path).unwrap();
+
+        session.load_project(&path).unwrap();
+        assert_eq!(session.shader_source(), Some(TRIANGLE));
+
+        let missing = session.load_project(Path::new("/nonexistent/project.json"));
+        assert!(matches!(missing, Err(SessionError::Project(_))));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_render_frame() {
+        let Some(mut session) = session() else {
+            return;
+        };
+        assert!(matches!(
+            session.render_frame(4, 4),
+            Err(SessionError::Shader(_))
+        ));
+
+        session.set_shader(TRIANGLE);
+        let pixels = session.render_frame(4, 4).unwrap();
+        assert_eq!(pixels.len(), 4 * 4 * 4);
+        assert_eq!(&pixels[..4], &[255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_render_frame_rejects_vertex_buffers() {
+        let Some(mut session) = session() else {
+            return;
+        };
+        session.set_shader(
+            "@vertex fn vs(@location(0) p: vec4<f32>) -> @builtin(position) vec4<f32> { return p; }
+             @fragment fn fs() -> @location(0) vec4<f32> { return vec4<f32>(1.0); }",
+        );
+        assert!(matches!(
+            session.render_frame(4, 4),
+            Err(SessionError::Shader(_))
+        ));
+    }
+
+    #[test]
+    fn test_run_compute() {
+        let Some(mut session) = session() else {
+            return;
+        };
+        session.set_shader(DOUBLE);
+        let input: Vec<u8> = [1u32, 2, 3, 4]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let output = session.run_compute(None, &input, 16, [4, 1, 1]).unwrap();
+        let values: Vec<u32> = output
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        assert_eq!(values, vec![2, 4, 6, 8]);
+
+        assert!(matches!(
+            session.run_compute(Some("missing"), &input, 16, [1, 1, 1]),
+            Err(SessionError::Shader(_))
+        ));
+    }
+}
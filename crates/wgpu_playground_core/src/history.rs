@@ -0,0 +1,399 @@
+//! Undo/redo history for playground state
+//!
+//! Panel edits are recorded as snapshots of the serializable
+//! [`PlaygroundState`], so the panels that export and import their state
+//! (listed in [`UNDO_SECTIONS`]) take part in undo/redo without their own
+//! command types; other panels keep their settings when stepping through
+//! the history. Consecutive edits to the same panels within
+//! [`COALESCE_SECONDS`] are merged into one entry, so typing in the shader
+//! editor or dragging a slider produces a single step.
+//!
+//! Snapshots are only taken when an edit is reported with
+//! [`History::mark_edited`], not on every frame.
+//!
+//! Preferences and progress (theme, tutorial state, learning progress and
+//! API coverage) are not part of the history.
+
+use std::collections::VecDeque;
+
+use serde_json::Value;
+
+use crate::state::PlaygroundState;
+
+/// Default number of entries kept, including the initial state
+pub const DEFAULT_CAPACITY: usize = 100;
+
+/// Edits to the same sections closer together than this are merged
+pub const COALESCE_SECONDS: f64 = 1.0;
+
+/// State sections recorded in the history, in the order shown to the user
+pub const UNDO_SECTIONS: &[&str] = &[
+    "buffer_panel",
+    "texture_panel",
+    "sampler_panel",
+    "shader_editor",
+    "render_pipeline_panel",
+    "compute_pipeline_panel",
+    "bind_group_panel",
+    "bind_group_layout_panel",
+];
+
+/// Display names of the panels covered by undo/redo
+pub fn undo_panel_names() -> Vec<String> {
+    UNDO_SECTIONS.iter().map(|key| section_name(key)).collect()
+}
+
+/// One recorded state
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    /// Short description, e.g. "Buffer panel, Sampler panel"
+    pub label: String,
+    /// Time of the last edit merged into this entry, in seconds
+    pub time: f64,
+    /// Top-level state sections that changed relative to the previous entry
+    sections: Vec<String>,
+    snapshot: Value,
+}
+
+/// Bounded stack of state snapshots with an undo cursor
+#[derive(Debug, Clone)]
+pub struct History {
+    entries: VecDeque<HistoryEntry>,
+    /// Index of the entry matching the current state
+    cursor: usize,
+    capacity: usize,
+    /// Set when an edit is reported and cleared when it is recorded
+    edited: bool,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+/// Serialize the parts of the state that take part in undo/redo
+fn snapshot(state: &PlaygroundState) -> Value {
+    let mut state = state.clone();
+    state.theme = Default::default();
+    state.api_coverage = None;
+    state.tutorial_state = None;
+    state.learning_progress = None;
+    serde_json::to_value(&state).unwrap_or(Value::Null)
+}
+
+/// Top-level keys whose values differ between two snapshots
+fn changed_sections(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+    let mut sections: Vec<String> = after
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    sections.extend(
+        before
+            .keys()
+            .filter(|key| !after.contains_key(*key))
+            .cloned(),
+    );
+    sections
+}
+
+/// "shader_editor" -> "Shader editor"
+fn section_name(key: &str) -> String {
+    let name = key.replace('_', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+impl History {
+    /// Create an empty history keeping at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            cursor: 0,
+            capacity: capacity.max(2),
+            edited: false,
+        }
+    }
+
+    /// Forget all entries and start again from `state`
+    pub fn reset(&mut self, state: &PlaygroundState, time: f64) {
+        self.entries.clear();
+        self.entries.push_back(HistoryEntry {
+            label: "Initial state".to_string(),
+            time,
+            sections: Vec::new(),
+            snapshot: snapshot(state),
+        });
+        self.cursor = 0;
+        self.edited = false;
+    }
+
+    /// Report that the state may have changed since the last record
+    pub fn mark_edited(&mut self) {
+        self.edited = true;
+    }
+
+    /// Whether an edit was reported, clearing the flag
+    ///
+    /// Callers export the state and [`record`](Self::record) it only when
+    /// this returns true.
+    pub fn take_edited(&mut self) -> bool {
+        std::mem::take(&mut self.edited)
+    }
+
+    /// Record the current state after an edit
+    ///
+    /// Does nothing if the state matches the current entry. Otherwise any
+    /// redo entries are discarded and a new entry is pushed, or merged into
+    /// the current one if it touched the same sections within
+    /// [`COALESCE_SECONDS`]. Returns true if the history changed.
+    pub fn record(&mut self, state: &PlaygroundState, time: f64) -> bool {
+        let snapshot = snapshot(state);
+        let Some(current) = self.entries.get(self.cursor) else {
+            self.reset(state, time);
+            return true;
+        };
+        if current.snapshot == snapshot {
+            return false;
+        }
+
+        let sections = changed_sections(&current.snapshot, &snapshot);
+        let at_end = self.cursor + 1 == self.entries.len();
+        let coalesce = at_end
+            && self.cursor > 0
+            && time - current.time < COALESCE_SECONDS
+            && current.sections
+                == changed_sections(&self.entries[self.cursor - 1].snapshot, &snapshot);
+
+        if coalesce {
+            let entry = &mut self.entries[self.cursor];
+            entry.snapshot = snapshot;
+            entry.time = time;
+            return true;
+        }
+
+        self.entries.truncate(self.cursor + 1);
+        let label = sections
+            .iter()
+            .map(|s| section_name(s))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.entries.push_back(HistoryEntry {
+            label,
+            time,
+            sections,
+            snapshot,
+        });
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+        self.cursor = self.entries.len() - 1;
+        true
+    }
+
+    /// Replace the current entry's snapshot without creating a new entry
+    ///
+    /// Call after applying a restored state, since panels may normalize
+    /// values on import and the exported state can differ slightly.
+    pub fn sync(&mut self, state: &PlaygroundState) {
+        if let Some(entry) = self.entries.get_mut(self.cursor) {
+            entry.snapshot = snapshot(state);
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.cursor + 1 < self.entries.len()
+    }
+
+    /// Step back one entry, returning the state to apply
+    pub fn undo(&mut self) -> Option<PlaygroundState> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.jump_to(self.cursor - 1)
+    }
+
+    /// Step forward one entry, returning the state to apply
+    pub fn redo(&mut self) -> Option<PlaygroundState> {
+        if !self.can_redo() {
+            return None;
+        }
+        self.jump_to(self.cursor + 1)
+    }
+
+    /// Move the cursor to any entry, returning the state to apply
+    pub fn jump_to(&mut self, index: usize) -> Option<PlaygroundState> {
+        let entry = self.entries.get(index)?;
+        let state = serde_json::from_value(entry.snapshot.clone()).ok()?;
+        self.cursor = index;
+        Some(state)
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Index of the entry matching the current state
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Label of the entry that [`Self::undo`] would revert
+    pub fn undo_label(&self) -> Option<&str> {
+        self.can_undo()
+            .then(|| self.entries[self.cursor].label.as_str())
+    }
+
+    /// Label of the entry that [`Self::redo`] would reapply
+    pub fn redo_label(&self) -> Option<&str> {
+        self.entries
+            .get(self.cursor + 1)
+            .map(|entry| entry.label.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{BufferPanelState, SamplerPanelState};
+
+    fn state(buffer_label: &str) -> PlaygroundState {
+        PlaygroundState {
+            buffer_panel: Some(BufferPanelState {
+                label: buffer_label.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn buffer_label(state: &PlaygroundState) -> &str {
+        &state.buffer_panel.as_ref().unwrap().label
+    }
+
+    #[test]
+    fn test_record_and_undo_redo() {
+        let mut history = History::default();
+        history.reset(&state("a"), 0.0);
+        assert!(!history.can_undo());
+
+        assert!(history.record(&state("b"), 2.0));
+        assert!(history.record(&state("c"), 4.0));
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.undo_label(), Some("Buffer panel"));
+
+        assert_eq!(buffer_label(&history.undo().unwrap()), "b");
+        assert_eq!(buffer_label(&history.undo().unwrap()), "a");
+        assert!(history.undo().is_none());
+        assert_eq!(buffer_label(&history.redo().unwrap()), "b");
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn test_unchanged_state_is_not_recorded() {
+        let mut history = History::default();
+        history.reset(&state("a"), 0.0);
+        assert!(!history.record(&state("a"), 5.0));
+
+        // Preferences and progress are ignored
+        let mut themed = state("a");
+        themed.theme = crate::state::Theme::Light;
+        assert!(!history.record(&themed, 6.0));
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_new_edit_discards_redo() {
+        let mut history = History::default();
+        history.reset(&state("a"), 0.0);
+        history.record(&state("b"), 2.0);
+        history.undo();
+        history.record(&state("x"), 4.0);
+        assert!(!history.can_redo());
+        assert_eq!(history.len(), 2);
+        assert_eq!(buffer_label(&history.undo().unwrap()), "a");
+    }
+
+    #[test]
+    fn test_rapid_edits_coalesce() {
+        let mut history = History::default();
+        history.reset(&state("a"), 0.0);
+        history.record(&state("ab"), 2.0);
+        history.record(&state("abc"), 2.3);
+        history.record(&state("abcd"), 2.6);
+        assert_eq!(history.len(), 2);
+
+        // A different section starts a new entry even when quick
+        let mut other = state("abcd");
+        other.sampler_panel = Some(SamplerPanelState::default());
+        history.record(&other, 2.8);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.undo_label(), Some("Sampler panel"));
+
+        // A pause starts a new entry for the same section
+        let mut later = other.clone();
+        later.buffer_panel = state("abcde").buffer_panel;
+        history.record(&later, 10.0);
+        assert_eq!(history.len(), 4);
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let mut history = History::new(3);
+        history.reset(&state("0"), 0.0);
+        for i in 1..5 {
+            history.record(&state(&i.to_string()), i as f64 * 2.0);
+        }
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.cursor(), 2);
+        assert_eq!(buffer_label(&history.jump_to(0).unwrap()), "2");
+    }
+
+    #[test]
+    fn test_undo_sections_are_state_keys() {
+        let snapshot = snapshot(&PlaygroundState::default());
+        for key in UNDO_SECTIONS {
+            assert!(snapshot.get(key).is_some(), "{} is not a state key", key);
+        }
+        assert_eq!(undo_panel_names()[3], "Shader editor");
+    }
+
+    #[test]
+    fn test_edited_flag_is_taken_once() {
+        let mut history = History::default();
+        history.reset(&state("a"), 0.0);
+        assert!(!history.take_edited());
+
+        history.mark_edited();
+        assert!(history.take_edited());
+        assert!(!history.take_edited());
+
+        history.mark_edited();
+        history.reset(&state("b"), 1.0);
+        assert!(!history.take_edited());
+    }
+}
@@ -0,0 +1,150 @@
+//! History browser for undo/redo
+//!
+//! Lists the entries of a [`History`] and lets the user step or jump to any
+//! of them. The panel only moves the history cursor; the returned state is
+//! applied to the other panels by the caller.
+
+use crate::history::{undo_panel_names, History};
+use crate::state::PlaygroundState;
+
+/// Panel listing undo/redo history entries
+pub struct HistoryPanel {
+    /// Show the newest entries first
+    newest_first: bool,
+}
+
+impl Default for HistoryPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistoryPanel {
+    pub fn new() -> Self {
+        Self { newest_first: true }
+    }
+
+    /// Render the panel; returns a state to apply if the user moved in history
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        history: &mut History,
+        now: f64,
+    ) -> Option<PlaygroundState> {
+        let mut restore = None;
+
+        ui.heading("🕘 History");
+        ui.label(
+            "Undo with Ctrl+Z, redo with Ctrl+Y or Ctrl+Shift+Z, or click an entry to jump to it.",
+        );
+        ui.label(format!(
+            "Edits to these panels are recorded: {}. Other panels keep their settings.",
+            undo_panel_names().join(", ")
+        ));
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            let undo = ui.add_enabled(history.can_undo(), egui::Button::new("↶ Undo"));
+            let undo = match history.undo_label() {
+                Some(label) => undo.on_hover_text(format!("Undo {}", label)),
+                None => undo,
+            };
+            if undo.clicked() {
+                restore = history.undo();
+            }
+
+            let redo = ui.add_enabled(history.can_redo(), egui::Button::new("↷ Redo"));
+            let redo = match history.redo_label() {
+                Some(label) => redo.on_hover_text(format!("Redo {}", label)),
+                None => redo,
+            };
+            if redo.clicked() {
+                restore = history.redo();
+            }
+
+            ui.separator();
+            ui.checkbox(&mut self.newest_first, "Newest first");
+            ui.label(format!(
+                "{} / {} entries",
+                history.len(),
+                history.capacity()
+            ));
+        });
+        ui.add_space(5.0);
+
+        ui.group(|ui| {
+            egui::ScrollArea::vertical()
+                .max_height(400.0)
+                .show(ui, |ui| {
+                    let entries: Vec<_> = history
+                        .entries()
+                        .map(|entry| (entry.label.clone(), entry.time))
+                        .enumerate()
+                        .collect();
+                    let cursor = history.cursor();
+                    let ordered: Box<dyn Iterator<Item = _>> = if self.newest_first {
+                        Box::new(entries.into_iter().rev())
+                    } else {
+                        Box::new(entries.into_iter())
+                    };
+
+                    egui::Grid::new("history_entries")
+                        .num_columns(3)
+                        .spacing([20.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (index, (label, time)) in ordered {
+                                let text = if index == cursor {
+                                    egui::RichText::new(format!("▶ {}", label))
+                                        .color(egui::Color32::from_rgb(255, 200, 100))
+                                } else if index > cursor {
+                                    // Undone entries that redo would reapply
+                                    egui::RichText::new(&label).weak()
+                                } else {
+                                    egui::RichText::new(&label)
+                                };
+                                ui.label(format!("#{}", index));
+                                if ui
+                                    .selectable_label(index == cursor, text)
+                                    .on_hover_text("Restore this state")
+                                    .clicked()
+                                    && index != cursor
+                                {
+                                    restore = history.jump_to(index);
+                                }
+                                ui.label(format_age(now - time));
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+        restore
+    }
+}
+
+/// "just now", "42s ago", "3m ago"
+fn format_age(seconds: f64) -> String {
+    if seconds < 1.0 {
+        "just now".to_string()
+    } else if seconds < 60.0 {
+        format!("{:.0}s ago", seconds)
+    } else if seconds < 3600.0 {
+        format!("{:.0}m ago", seconds / 60.0)
+    } else {
+        format!("{:.0}h ago", seconds / 3600.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(0.2), "just now");
+        assert_eq!(format_age(42.0), "42s ago");
+        assert_eq!(format_age(180.0), "3m ago");
+        assert_eq!(format_age(7200.0), "2h ago");
+    }
+}
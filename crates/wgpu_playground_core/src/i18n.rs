@@ -0,0 +1,514 @@
+//! Translations of the user interface
+//!
+//! Text shown in the GUI is looked up by key in the message catalog of the
+//! selected language with [`tr`], falling back to English for messages a
+//! translation doesn't have yet. Catalogs are written in a subset of the
+//! [Fluent](https://projectfluent.org) syntax:
+//!
+//! ```text
+//! # A comment
+//! nav-buffers = Buffers
+//!     .tooltip = Create and configure GPU buffers
+//! settings-cache-limit-set = ✓ Cache limit set to { $mib } MiB
+//! ```
+//!
+//! Attributes such as `.tooltip` are looked up as `nav-buffers.tooltip`,
+//! indented lines continue the message on a new line, and `{ $name }`
+//! placeables are filled in by [`tr_args`]. Terms, selectors and functions
+//! are not supported.
+//!
+//! The catalogs in the crate's `locales` directory are built in. Files in the
+//! `locales` subdirectory of the [assets directory](crate::assets::assets_dir)
+//! are loaded on top of them, so a translation can be tried or extended
+//! without rebuilding.
+
+use crate::assets;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// Language every key is defined in, used for missing translations
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Key of the message holding a language's own name
+const LANGUAGE_NAME_KEY: &str = "language-name";
+
+/// Catalogs built into the crate, by language code
+const BUILTIN_CATALOGS: [(&str, &str); 3] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+];
+
+/// Error in the syntax of a message catalog
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Messages of one language
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    language: String,
+    messages: BTreeMap<String, String>,
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl Catalog {
+    /// Parse the messages of `language` from the catalog syntax
+    pub fn parse(language: &str, source: &str) -> Result<Self, ParseError> {
+        let mut messages = BTreeMap::new();
+        // Key of the message or attribute that indented lines continue
+        let mut current: Option<String> = None;
+        let mut message: Option<String> = None;
+
+        for (index, line) in source.lines().enumerate() {
+            let error = |message: String| ParseError {
+                line: index + 1,
+                message,
+            };
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if line.starts_with('#') {
+                current = None;
+                message = None;
+                continue;
+            }
+
+            if line.starts_with(char::is_whitespace) {
+                if let Some(attribute) = trimmed.strip_prefix('.') {
+                    let Some(message) = &message else {
+                        return Err(error("attribute outside of a message".to_string()));
+                    };
+                    let (name, value) = attribute
+                        .split_once('=')
+                        .ok_or_else(|| error(format!("expected `=` after .{}", attribute)))?;
+                    let name = name.trim();
+                    if !is_identifier(name) {
+                        return Err(error(format!("invalid attribute name `{}`", name)));
+                    }
+                    let key = format!("{}.{}", message, name);
+                    if messages
+                        .insert(key.clone(), value.trim().to_string())
+                        .is_some()
+                    {
+                        return Err(error(format!("duplicate attribute `{}`", key)));
+                    }
+                    current = Some(key);
+                } else {
+                    let Some(key) = &current else {
+                        return Err(error("indented line outside of a message".to_string()));
+                    };
+                    let value = messages.entry(key.clone()).or_default();
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(trimmed);
+                }
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `key = value`".to_string()))?;
+            let key = key.trim();
+            if !is_identifier(key) {
+                return Err(error(format!("invalid message key `{}`", key)));
+            }
+            if messages
+                .insert(key.to_string(), value.trim().to_string())
+                .is_some()
+            {
+                return Err(error(format!("duplicate message `{}`", key)));
+            }
+            current = Some(key.to_string());
+            message = Some(key.to_string());
+        }
+
+        // Messages with only attributes have no value of their own
+        messages.retain(|_, value| !value.is_empty());
+        Ok(Self {
+            language: language.to_string(),
+            messages,
+        })
+    }
+
+    /// Code of the language, e.g. `de`
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Name of the language in the language itself, or its code
+    pub fn name(&self) -> &str {
+        self.get(LANGUAGE_NAME_KEY).unwrap_or(&self.language)
+    }
+
+    /// Message with the given key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// Keys of every message, in order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Add the messages of `other`, replacing those with the same key
+    pub fn merge(&mut self, other: Catalog) {
+        self.messages.extend(other.messages);
+    }
+}
+
+/// Language that can be selected, with how much of it is translated
+#[derive(Debug, Clone, PartialEq)]
+pub struct Language {
+    pub code: String,
+    pub name: String,
+    /// Fraction of the English messages the language has, between 0 and 1
+    pub coverage: f32,
+}
+
+/// Fill `{ $name }` placeables of a message with the given arguments
+///
+/// Placeables without an argument are left as they are.
+pub fn format_message(message: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let placeable = &rest[start..=end];
+        let name = placeable[1..placeable.len() - 1]
+            .trim()
+            .strip_prefix('$')
+            .map(str::trim);
+        match name.and_then(|name| args.iter().find(|(arg, _)| *arg == name)) {
+            Some((_, value)) => result.push_str(&value.to_string()),
+            None => result.push_str(placeable),
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Message catalogs of every language and the selected one
+#[derive(Debug, Clone)]
+pub struct Translator {
+    /// Catalogs by language code, always including [`FALLBACK_LANGUAGE`]
+    catalogs: BTreeMap<String, Catalog>,
+    language: String,
+}
+
+impl Default for Translator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Translator {
+    /// Translator with the built-in catalogs, showing English
+    pub fn new() -> Self {
+        let catalogs = BUILTIN_CATALOGS
+            .iter()
+            .map(|(language, source)| {
+                let catalog = Catalog::parse(language, source).unwrap_or_else(|e| {
+                    panic!("The built-in {} catalog is invalid: {}", language, e)
+                });
+                (language.to_string(), catalog)
+            })
+            .collect();
+        Self {
+            catalogs,
+            language: FALLBACK_LANGUAGE.to_string(),
+        }
+    }
+
+    /// Add a catalog, merging it into an existing one of the same language
+    pub fn add_catalog(&mut self, catalog: Catalog) {
+        match self.catalogs.get_mut(catalog.language()) {
+            Some(existing) => existing.merge(catalog),
+            None => {
+                self.catalogs.insert(catalog.language.clone(), catalog);
+            }
+        }
+    }
+
+    /// Add every `<language>.ftl` catalog in a directory
+    ///
+    /// Returns the number of files loaded, or one error per file that could
+    /// not be read or parsed; the other files are still loaded.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize, Vec<String>> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(vec![format!("{}: {}", dir.display(), e)]),
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ftl"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        let mut errors = Vec::new();
+        for path in paths {
+            let Some(language) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let catalog = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|source| Catalog::parse(language, &source).map_err(|e| e.to_string()));
+            match catalog {
+                Ok(catalog) => {
+                    self.add_catalog(catalog);
+                    loaded += 1;
+                }
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(loaded)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Code of the selected language
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Select a language, returning false if there is no catalog for it
+    pub fn set_language(&mut self, code: &str) -> bool {
+        if !self.catalogs.contains_key(code) {
+            return false;
+        }
+        self.language = code.to_string();
+        true
+    }
+
+    /// Catalog of a language
+    pub fn catalog(&self, code: &str) -> Option<&Catalog> {
+        self.catalogs.get(code)
+    }
+
+    fn fallback(&self) -> &Catalog {
+        &self.catalogs[FALLBACK_LANGUAGE]
+    }
+
+    /// Every language with a catalog, English first
+    pub fn languages(&self) -> Vec<Language> {
+        let mut languages: Vec<Language> = self
+            .catalogs
+            .values()
+            .map(|catalog| Language {
+                code: catalog.language().to_string(),
+                name: catalog.name().to_string(),
+                coverage: self.coverage(catalog.language()),
+            })
+            .collect();
+        languages.sort_by_key(|language| language.code != FALLBACK_LANGUAGE);
+        languages
+    }
+
+    /// English keys a language has no message for
+    pub fn missing_keys(&self, code: &str) -> Vec<&str> {
+        let catalog = self.catalogs.get(code);
+        self.fallback()
+            .keys()
+            .filter(|key| *key != LANGUAGE_NAME_KEY)
+            .filter(|key| catalog.is_none_or(|catalog| catalog.get(key).is_none()))
+            .collect()
+    }
+
+    /// Fraction of the English messages a language has, between 0 and 1
+    pub fn coverage(&self, code: &str) -> f32 {
+        let total = self.fallback().len().saturating_sub(1);
+        if total == 0 {
+            return 1.0;
+        }
+        1.0 - self.missing_keys(code).len() as f32 / total as f32
+    }
+
+    /// Message for `key` in the selected language
+    ///
+    /// Falls back to English, and to the key itself for unknown keys so
+    /// that they stand out in the UI.
+    pub fn translate(&self, key: &str) -> String {
+        self.catalogs
+            .get(&self.language)
+            .and_then(|catalog| catalog.get(key))
+            .or_else(|| self.fallback().get(key))
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    /// Message for `key` with its placeables filled in
+    pub fn translate_args(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        format_message(&self.translate(key), args)
+    }
+}
+
+/// Translator used by [`tr`], with the community catalogs loaded on first use
+fn translator() -> &'static RwLock<Translator> {
+    static TRANSLATOR: OnceLock<RwLock<Translator>> = OnceLock::new();
+    TRANSLATOR.get_or_init(|| {
+        let mut translator = Translator::new();
+        if cfg!(not(target_arch = "wasm32")) {
+            if let Err(errors) = translator.load_dir(&community_dir()) {
+                for error in errors {
+                    log::warn!("Failed to load translation {}", error);
+                }
+            }
+        }
+        RwLock::new(translator)
+    })
+}
+
+fn read() -> std::sync::RwLockReadGuard<'static, Translator> {
+    match translator().read() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn write() -> std::sync::RwLockWriteGuard<'static, Translator> {
+    match translator().write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Directory community translations are loaded from
+pub fn community_dir() -> PathBuf {
+    assets::assets_dir().join("locales")
+}
+
+/// Message for `key` in the selected language
+pub fn tr(key: &str) -> String {
+    read().translate(key)
+}
+
+/// Message for `key` with its `{ $name }` placeables filled in
+pub fn tr_args(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    read().translate_args(key, args)
+}
+
+/// Code of the selected language
+pub fn language() -> String {
+    read().language().to_string()
+}
+
+/// Select the language of the UI, returning false if it has no catalog
+pub fn set_language(code: &str) -> bool {
+    write().set_language(code)
+}
+
+/// Every language that can be selected, English first
+pub fn languages() -> Vec<Language> {
+    read().languages()
+}
+
+/// Load the catalogs in [`community_dir`] again, e.g. after editing one
+pub fn reload_community_translations() -> Result<usize, Vec<String>> {
+    let mut translator = Translator::new();
+    let result = translator.load_dir(&community_dir());
+    let mut current = write();
+    translator.set_language(current.language());
+    *current = translator;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_messages_attributes_and_continuations() {
+        let catalog = Catalog::parse(
+            "xx",
+            "# comment\nlanguage-name = Test\n\nnav = Buffers\n    .tooltip = Create buffers\nmulti =\n    first\n    second\n",
+        )
+        .unwrap();
+        assert_eq!(catalog.name(), "Test");
+        assert_eq!(catalog.get("nav"), Some("Buffers"));
+        assert_eq!(catalog.get("nav.tooltip"), Some("Create buffers"));
+        assert_eq!(catalog.get("multi"), Some("first\nsecond"));
+        assert_eq!(catalog.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_errors_report_the_line() {
+        let error = Catalog::parse("xx", "a = 1\nnot a message\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(Catalog::parse("xx", "a = 1\na = 2\n").is_err());
+        assert!(Catalog::parse("xx", "    .tooltip = orphan\n").is_err());
+        assert!(Catalog::parse("xx", "1a = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_format_message_fills_placeables() {
+        assert_eq!(
+            format_message(
+                "{ $used } of {$limit} in { $dir }",
+                &[("used", &3), ("limit", &"8 MiB")]
+            ),
+            "3 of 8 MiB in { $dir }"
+        );
+        assert_eq!(format_message("no { closing", &[]), "no { closing");
+    }
+
+    #[test]
+    fn test_translate_falls_back_to_english_and_the_key() {
+        let mut translator = Translator::new();
+        translator.add_catalog(Catalog::parse("xx", "nav-buffers = Puffer\n").unwrap());
+        assert!(translator.set_language("xx"));
+        assert_eq!(translator.translate("nav-buffers"), "Puffer");
+        assert_eq!(translator.translate("nav-textures"), "Textures");
+        assert_eq!(translator.translate("no-such-key"), "no-such-key");
+        assert!(!translator.set_language("zz"));
+        assert_eq!(translator.language(), "xx");
+    }
+
+    #[test]
+    fn test_coverage_and_languages() {
+        let mut translator = Translator::new();
+        assert_eq!(translator.coverage("en"), 1.0);
+        translator.add_catalog(Catalog::parse("xx", "nav-buffers = Puffer\n").unwrap());
+        assert!(translator.coverage("xx") > 0.0 && translator.coverage("xx") < 0.1);
+
+        let languages = translator.languages();
+        assert_eq!(languages[0].code, "en");
+        let xx = languages.iter().find(|l| l.code == "xx").unwrap();
+        assert_eq!(xx.name, "xx");
+    }
+}
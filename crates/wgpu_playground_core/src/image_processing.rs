@@ -0,0 +1,773 @@
+//! Compute-shader image filters for the image processing examples
+//!
+//! Every filter reads a sampled source texture and writes an `rgba8unorm`
+//! storage texture, one invocation per pixel in 8x8 workgroups:
+//!
+//! - Gaussian blur runs one entry point twice, horizontally into an
+//!   intermediate texture and then vertically into the output
+//! - Sobel edge detection computes the luminance gradient of a 3x3
+//!   neighborhood
+//! - Histogram equalization counts luminance values with atomics, turns the
+//!   histogram into a lookup table in a single-invocation pass and remaps
+//!   every pixel through it
+//!
+//! All shaders share one bind group layout, so a filter only declares the
+//! bindings it uses.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::upload_belt;
+
+/// Largest blur radius in pixels
+pub const MAX_BLUR_RADIUS: u32 = 32;
+
+/// Number of luminance bins of the histogram
+const HISTOGRAM_BINS: u64 = 256;
+
+const WORKGROUP_SIZE: u32 = 8;
+
+const OUTPUT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+pub const GAUSSIAN_BLUR_SHADER: &str = r#"// Gaussian Blur Example
+// A separable blur: the host runs this entry point twice, horizontally into
+// an intermediate storage texture and then vertically into the output
+
+struct Params {
+    direction: vec2<i32>,
+    radius: i32,
+    sigma: f32,
+    strength: f32,
+    amount: f32,
+}
+
+@group(0) @binding(0) var input: texture_2d<f32>;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(2) var<uniform> params: Params;
+
+@compute @workgroup_size(8, 8)
+fn blur(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = vec2<i32>(textureDimensions(input));
+    let pixel = vec2<i32>(id.xy);
+    if pixel.x >= size.x || pixel.y >= size.y {
+        return;
+    }
+
+    var sum = vec4<f32>(0.0);
+    var weight_sum = 0.0;
+    for (var i = -params.radius; i <= params.radius; i++) {
+        // Clamp to the edge so border pixels are not darkened
+        let coord = clamp(pixel + params.direction * i, vec2<i32>(0), size - 1);
+        let x = f32(i);
+        let weight = exp(-(x * x) / (2.0 * params.sigma * params.sigma));
+        sum += textureLoad(input, coord, 0) * weight;
+        weight_sum += weight;
+    }
+    textureStore(output, pixel, sum / weight_sum);
+}
+"#;
+
+pub const SOBEL_SHADER: &str = r#"// Sobel Edge Detection Example
+// Approximates the luminance gradient with the two 3x3 Sobel kernels
+
+struct Params {
+    direction: vec2<i32>,
+    radius: i32,
+    sigma: f32,
+    strength: f32,
+    amount: f32,
+}
+
+@group(0) @binding(0) var input: texture_2d<f32>;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(2) var<uniform> params: Params;
+
+fn luminance_at(coord: vec2<i32>, size: vec2<i32>) -> f32 {
+    let color = textureLoad(input, clamp(coord, vec2<i32>(0), size - 1), 0).rgb;
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+@compute @workgroup_size(8, 8)
+fn sobel(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = vec2<i32>(textureDimensions(input));
+    let pixel = vec2<i32>(id.xy);
+    if pixel.x >= size.x || pixel.y >= size.y {
+        return;
+    }
+
+    let tl = luminance_at(pixel + vec2<i32>(-1, -1), size);
+    let t = luminance_at(pixel + vec2<i32>(0, -1), size);
+    let tr = luminance_at(pixel + vec2<i32>(1, -1), size);
+    let l = luminance_at(pixel + vec2<i32>(-1, 0), size);
+    let r = luminance_at(pixel + vec2<i32>(1, 0), size);
+    let bl = luminance_at(pixel + vec2<i32>(-1, 1), size);
+    let b = luminance_at(pixel + vec2<i32>(0, 1), size);
+    let br = luminance_at(pixel + vec2<i32>(1, 1), size);
+
+    let gx = (tr + 2.0 * r + br) - (tl + 2.0 * l + bl);
+    let gy = (bl + 2.0 * b + br) - (tl + 2.0 * t + tr);
+    let edge = clamp(length(vec2<f32>(gx, gy)) * params.strength, 0.0, 1.0);
+
+    // `amount` keeps a dimmed copy of the original under the edges
+    let original = textureLoad(input, pixel, 0).rgb;
+    textureStore(output, pixel, vec4<f32>(max(vec3<f32>(edge), original * params.amount), 1.0));
+}
+"#;
+
+pub const HISTOGRAM_EQUALIZATION_SHADER: &str = r#"// Histogram Equalization Example
+// Three passes over the same bindings:
+// 1. build_histogram counts pixel luminance into 256 bins with atomics
+// 2. build_lut turns the cumulative histogram into a lookup table
+// 3. equalize remaps every pixel's luminance through the table
+
+struct Params {
+    direction: vec2<i32>,
+    radius: i32,
+    sigma: f32,
+    strength: f32,
+    amount: f32,
+}
+
+@group(0) @binding(0) var input: texture_2d<f32>;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(2) var<uniform> params: Params;
+@group(0) @binding(3) var<storage, read_write> histogram: array<atomic<u32>, 256>;
+@group(0) @binding(4) var<storage, read_write> lut: array<f32, 256>;
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+fn bin(color: vec3<f32>) -> u32 {
+    return u32(clamp(luminance(color), 0.0, 1.0) * 255.0 + 0.5);
+}
+
+@compute @workgroup_size(8, 8)
+fn build_histogram(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(input);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+    let color = textureLoad(input, vec2<i32>(id.xy), 0).rgb;
+    atomicAdd(&histogram[bin(color)], 1u);
+}
+
+@compute @workgroup_size(1)
+fn build_lut() {
+    let size = textureDimensions(input);
+    let total = size.x * size.y;
+
+    // The darkest luminance present maps to black, the brightest to white
+    var cdf = 0u;
+    var cdf_min = 0u;
+    for (var i = 0u; i < 256u; i++) {
+        cdf += atomicLoad(&histogram[i]);
+        if cdf_min == 0u {
+            cdf_min = cdf;
+        }
+        lut[i] = f32(cdf - cdf_min) / f32(max(total - cdf_min, 1u));
+    }
+}
+
+@compute @workgroup_size(8, 8)
+fn equalize(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(input);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+    let color = textureLoad(input, vec2<i32>(id.xy), 0);
+    let equalized = lut[bin(color.rgb)];
+    let scaled = clamp(color.rgb * (equalized / max(luminance(color.rgb), 0.0001)), vec3<f32>(0.0), vec3<f32>(1.0));
+    textureStore(output, vec2<i32>(id.xy), vec4<f32>(mix(color.rgb, scaled, params.amount), color.a));
+}
+"#;
+
+/// An image filter of the image processing examples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFilter {
+    GaussianBlur,
+    SobelEdges,
+    HistogramEqualization,
+}
+
+impl ImageFilter {
+    pub fn all() -> &'static [ImageFilter] {
+        &[
+            ImageFilter::GaussianBlur,
+            ImageFilter::SobelEdges,
+            ImageFilter::HistogramEqualization,
+        ]
+    }
+
+    /// Id of the example showing this filter
+    pub fn example_id(&self) -> &'static str {
+        match self {
+            ImageFilter::GaussianBlur => "gaussian_blur",
+            ImageFilter::SobelEdges => "sobel_edges",
+            ImageFilter::HistogramEqualization => "histogram_equalization",
+        }
+    }
+
+    pub fn from_example_id(id: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|filter| filter.example_id() == id)
+    }
+
+    pub fn shader(&self) -> &'static str {
+        match self {
+            ImageFilter::GaussianBlur => GAUSSIAN_BLUR_SHADER,
+            ImageFilter::SobelEdges => SOBEL_SHADER,
+            ImageFilter::HistogramEqualization => HISTOGRAM_EQUALIZATION_SHADER,
+        }
+    }
+}
+
+/// Adjustable parameters of the filters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterParams {
+    /// Blur radius in pixels, up to [`MAX_BLUR_RADIUS`]
+    pub blur_radius: u32,
+    /// Standard deviation of the blur kernel in pixels
+    pub blur_sigma: f32,
+    /// Multiplier of the gradient magnitude
+    pub edge_strength: f32,
+    /// Brightness of the original image under the edges (0 = edges only)
+    pub edge_underlay: f32,
+    /// Blend between the original (0) and the equalized image (1)
+    pub equalize_amount: f32,
+}
+
+impl Default for FilterParams {
+    fn default() -> Self {
+        Self {
+            blur_radius: 8,
+            blur_sigma: 4.0,
+            edge_strength: 1.0,
+            edge_underlay: 0.0,
+            equalize_amount: 1.0,
+        }
+    }
+}
+
+/// Matches the `Params` struct of the shaders
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct ParamsUniform {
+    direction: [i32; 2],
+    radius: i32,
+    sigma: f32,
+    strength: f32,
+    amount: f32,
+    _padding: [f32; 2],
+}
+
+impl ParamsUniform {
+    fn new(filter: ImageFilter, params: &FilterParams, direction: [i32; 2]) -> Self {
+        Self {
+            direction,
+            radius: params.blur_radius.min(MAX_BLUR_RADIUS) as i32,
+            sigma: params.blur_sigma.max(0.1),
+            strength: params.edge_strength,
+            amount: match filter {
+                ImageFilter::SobelEdges => params.edge_underlay,
+                _ => params.equalize_amount,
+            },
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+/// A low-contrast RGBA test image, used when no texture is loaded
+///
+/// Soft gradients, a checkerboard and a disc squeezed into the middle of the
+/// value range give every filter something to work on.
+pub fn test_pattern(width: u32, height: u32) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let u = x as f32 / width.max(1) as f32;
+            let v = y as f32 / height.max(1) as f32;
+            let checker = if ((x / 32) + (y / 32)) % 2 == 0 {
+                0.15
+            } else {
+                0.0
+            };
+            let (dx, dy) = (u - 0.6, v - 0.45);
+            let disc = if dx * dx + dy * dy < 0.06 { 0.2 } else { 0.0 };
+            let base = 0.3 + 0.1 * u + checker + disc;
+            let to_u8 = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            pixels.extend_from_slice(&[
+                to_u8(base + 0.05 * v),
+                to_u8(base),
+                to_u8(base + 0.1 * (1.0 - u)),
+                255,
+            ]);
+        }
+    }
+    pixels
+}
+
+/// Textures and bind groups of the current image
+struct ImageTextures {
+    width: u32,
+    height: u32,
+    source: wgpu::Texture,
+    output: wgpu::Texture,
+    source_view: wgpu::TextureView,
+    output_view: wgpu::TextureView,
+    /// Source to intermediate, horizontal parameters
+    horizontal: wgpu::BindGroup,
+    /// Intermediate to output
+    vertical: wgpu::BindGroup,
+    /// Source to output
+    direct: wgpu::BindGroup,
+}
+
+/// Pipelines and resources for running the filters on one image
+pub struct ImageProcessor {
+    layout: wgpu::BindGroupLayout,
+    blur: wgpu::ComputePipeline,
+    sobel: wgpu::ComputePipeline,
+    build_histogram: wgpu::ComputePipeline,
+    build_lut: wgpu::ComputePipeline,
+    equalize: wgpu::ComputePipeline,
+    horizontal_params: wgpu::Buffer,
+    params: wgpu::Buffer,
+    histogram: wgpu::Buffer,
+    lut: wgpu::Buffer,
+    images: Option<ImageTextures>,
+    /// Texture IDs for egui display (source, output)
+    texture_ids: Option<(egui::TextureId, egui::TextureId)>,
+    /// Whether `texture_ids` refer to older textures
+    texture_ids_stale: bool,
+}
+
+impl ImageProcessor {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        let storage_buffer_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Image Processing Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: OUTPUT_FORMAT,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_buffer_entry(3),
+                storage_buffer_entry(4),
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Image Processing Pipeline Layout"),
+            bind_group_layouts: &[Some(&layout)],
+            immediate_size: 0,
+        });
+
+        let create_module = |label, source: &'static str| {
+            tracker.record(ApiCategory::Shader, "create_shader_module");
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            })
+        };
+        let blur_module = create_module("Gaussian Blur Shader", GAUSSIAN_BLUR_SHADER);
+        let sobel_module = create_module("Sobel Shader", SOBEL_SHADER);
+        let histogram_module = create_module(
+            "Histogram Equalization Shader",
+            HISTOGRAM_EQUALIZATION_SHADER,
+        );
+
+        let create_pipeline = |module: &wgpu::ShaderModule, entry_point| {
+            tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+
+        let create_buffer = |label, size, usage| {
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let uniform_usage = wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST;
+        let params_size = std::mem::size_of::<ParamsUniform>() as u64;
+
+        Self {
+            blur: create_pipeline(&blur_module, "blur"),
+            sobel: create_pipeline(&sobel_module, "sobel"),
+            build_histogram: create_pipeline(&histogram_module, "build_histogram"),
+            build_lut: create_pipeline(&histogram_module, "build_lut"),
+            equalize: create_pipeline(&histogram_module, "equalize"),
+            layout,
+            horizontal_params: create_buffer(
+                "Image Processing Horizontal Params",
+                params_size,
+                uniform_usage,
+            ),
+            params: create_buffer("Image Processing Params", params_size, uniform_usage),
+            histogram: create_buffer(
+                "Image Processing Histogram",
+                HISTOGRAM_BINS * 4,
+                wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            ),
+            lut: create_buffer(
+                "Image Processing Lookup Table",
+                HISTOGRAM_BINS * 4,
+                wgpu::BufferUsages::STORAGE,
+            ),
+            images: None,
+            texture_ids: None,
+            texture_ids_stale: false,
+        }
+    }
+
+    /// Upload the RGBA8 image the filters read
+    pub fn set_image(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(), String> {
+        let max = device.limits().max_texture_dimension_2d;
+        if width == 0 || height == 0 || width > max || height > max {
+            return Err(format!(
+                "Image size {}x{} is outside 1..={} pixels",
+                width, height, max
+            ));
+        }
+        if rgba.len() != (width as usize) * (height as usize) * 4 {
+            return Err(format!(
+                "Expected {} bytes of RGBA data for {}x{}, got {}",
+                width as usize * height as usize * 4,
+                width,
+                height,
+                rgba.len()
+            ));
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let create_texture = |label, usage| {
+            tracker.record(ApiCategory::Texture, "create_texture");
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: OUTPUT_FORMAT,
+                usage,
+                view_formats: &[],
+            })
+        };
+        let source = create_texture(
+            "Image Processing Source",
+            wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
+        );
+        let storage_usage = wgpu::TextureUsages::STORAGE_BINDING
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC;
+        let intermediate = create_texture("Image Processing Intermediate", storage_usage);
+        let output = create_texture("Image Processing Output", storage_usage);
+
+        tracker.record(ApiCategory::Queue, "write_texture");
+        queue.write_texture(
+            source.as_image_copy(),
+            rgba,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        let source_view = source.create_view(&wgpu::TextureViewDescriptor::default());
+        let intermediate_view = intermediate.create_view(&wgpu::TextureViewDescriptor::default());
+        let output_view = output.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = |label, input, output, params: &wgpu::Buffer| {
+            tracker.record(ApiCategory::BindGroup, "create_bind_group");
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &self.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(input),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(output),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: params.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: self.histogram.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: self.lut.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+        let images = ImageTextures {
+            width,
+            height,
+            horizontal: bind_group(
+                "Image Processing Horizontal",
+                &source_view,
+                &intermediate_view,
+                &self.horizontal_params,
+            ),
+            vertical: bind_group(
+                "Image Processing Vertical",
+                &intermediate_view,
+                &output_view,
+                &self.params,
+            ),
+            direct: bind_group(
+                "Image Processing Direct",
+                &source_view,
+                &output_view,
+                &self.params,
+            ),
+            source,
+            output,
+            source_view,
+            output_view,
+        };
+        self.images = Some(images);
+        self.texture_ids_stale = self.texture_ids.is_some();
+        Ok(())
+    }
+
+    /// Size of the current image
+    pub fn size(&self) -> Option<(u32, u32)> {
+        self.images.as_ref().map(|i| (i.width, i.height))
+    }
+
+    /// Run `filter` on the current image, writing the output texture
+    pub fn process(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        filter: ImageFilter,
+        params: &FilterParams,
+    ) -> Result<(), String> {
+        let images = self.images.as_ref().ok_or("No image is loaded")?;
+        let tracker = ApiCoverageTracker::global();
+
+        upload_belt::write_buffer(
+            queue,
+            &self.horizontal_params,
+            0,
+            bytemuck::bytes_of(&ParamsUniform::new(filter, params, [1, 0])),
+        );
+        upload_belt::write_buffer(
+            queue,
+            &self.params,
+            0,
+            bytemuck::bytes_of(&ParamsUniform::new(filter, params, [0, 1])),
+        );
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Image Processing Encoder"),
+        });
+        if filter == ImageFilter::HistogramEqualization {
+            encoder.clear_buffer(&self.histogram, 0, None);
+        }
+        {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Image Processing Pass"),
+                timestamp_writes: None,
+            });
+            let workgroups_x = images.width.div_ceil(WORKGROUP_SIZE);
+            let workgroups_y = images.height.div_ceil(WORKGROUP_SIZE);
+            let mut dispatch =
+                |pipeline: &wgpu::ComputePipeline, bind_group: &wgpu::BindGroup, x: u32, y: u32| {
+                    pass.set_pipeline(pipeline);
+                    pass.set_bind_group(0, bind_group, &[]);
+                    pass.dispatch_workgroups(x, y, 1);
+                };
+            match filter {
+                ImageFilter::GaussianBlur => {
+                    dispatch(&self.blur, &images.horizontal, workgroups_x, workgroups_y);
+                    dispatch(&self.blur, &images.vertical, workgroups_x, workgroups_y);
+                }
+                ImageFilter::SobelEdges => {
+                    dispatch(&self.sobel, &images.direct, workgroups_x, workgroups_y);
+                }
+                ImageFilter::HistogramEqualization => {
+                    dispatch(
+                        &self.build_histogram,
+                        &images.direct,
+                        workgroups_x,
+                        workgroups_y,
+                    );
+                    dispatch(&self.build_lut, &images.direct, 1, 1);
+                    dispatch(&self.equalize, &images.direct, workgroups_x, workgroups_y);
+                }
+            }
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+        Ok(())
+    }
+
+    pub fn source_texture(&self) -> Option<&wgpu::Texture> {
+        self.images.as_ref().map(|i| &i.source)
+    }
+
+    pub fn output_texture(&self) -> Option<&wgpu::Texture> {
+        self.images.as_ref().map(|i| &i.output)
+    }
+
+    /// Register the source and output textures with egui and return their IDs
+    ///
+    /// Note: This method is only available when building for native targets.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_texture_ids(
+        &mut self,
+        device: &wgpu::Device,
+        renderer: &mut egui_wgpu::Renderer,
+    ) -> Option<(egui::TextureId, egui::TextureId)> {
+        if self.texture_ids_stale {
+            if let Some((source, output)) = self.texture_ids.take() {
+                renderer.free_texture(&source);
+                renderer.free_texture(&output);
+            }
+            self.texture_ids_stale = false;
+        }
+        if self.texture_ids.is_none() {
+            let images = self.images.as_ref()?;
+            let register = |view| {
+                renderer.register_native_texture(device, view, egui_wgpu::wgpu::FilterMode::Linear)
+            };
+            let source = register(&images.source_view);
+            let output = register(&images.output_view);
+            self.texture_ids = Some((source, output));
+        }
+        self.texture_ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_validate() {
+        for filter in ImageFilter::all() {
+            validate(filter.shader());
+        }
+    }
+
+    #[test]
+    fn test_example_ids_round_trip() {
+        for filter in ImageFilter::all() {
+            assert_eq!(
+                ImageFilter::from_example_id(filter.example_id()),
+                Some(*filter)
+            );
+        }
+        assert_eq!(ImageFilter::from_example_id("triangle"), None);
+    }
+
+    #[test]
+    fn test_params_uniform_layout() {
+        // The WGSL struct is 24 bytes; the buffer is padded to 32
+        assert_eq!(std::mem::size_of::<ParamsUniform>(), 32);
+
+        let params = FilterParams {
+            blur_radius: 100,
+            edge_underlay: 0.25,
+            ..FilterParams::default()
+        };
+        let uniform = ParamsUniform::new(ImageFilter::GaussianBlur, &params, [1, 0]);
+        assert_eq!(uniform.radius, MAX_BLUR_RADIUS as i32);
+        assert_eq!(uniform.direction, [1, 0]);
+        let uniform = ParamsUniform::new(ImageFilter::SobelEdges, &params, [0, 1]);
+        assert_eq!(uniform.amount, 0.25);
+    }
+
+    #[test]
+    fn test_pattern_is_low_contrast() {
+        let pixels = test_pattern(64, 48);
+        assert_eq!(pixels.len(), 64 * 48 * 4);
+        let greens: Vec<u8> = pixels.chunks(4).map(|p| p[1]).collect();
+        let min = *greens.iter().min().unwrap();
+        let max = *greens.iter().max().unwrap();
+        assert!(min > 40 && max < 215, "range {}..{}", min, max);
+        assert!(pixels.chunks(4).all(|p| p[3] == 255));
+    }
+}
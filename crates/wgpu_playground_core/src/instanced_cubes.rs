@@ -0,0 +1,568 @@
+//! Thousands of spinning cubes drawn with a per-instance vertex buffer
+//!
+//! The cube mesh lives in vertex buffer slot 0 with `VertexStepMode::Vertex`;
+//! the transform and color of every cube live in slot 1 with
+//! `VertexStepMode::Instance`, so the whole grid is one `draw_indexed` call.
+//!
+//! For comparison the same pipeline can draw the naive way: one
+//! `set_vertex_buffer` and one draw per cube, binding a single-instance
+//! slice of the instance buffer each time. Both paths produce the same
+//! image; only the CPU time to record and submit the frame differs.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::cross;
+use crate::upload_belt;
+use std::time::{Duration, Instant};
+use wgpu::util::DeviceExt;
+
+/// Largest number of cubes; the instance buffer is allocated for this many
+pub const MAX_INSTANCES: u32 = 20_000;
+
+/// Distance between neighboring cube centers
+const SPACING: f32 = 1.6;
+
+/// Weight of the newest frame in the smoothed frame times
+const SMOOTHING: f32 = 0.1;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub const INSTANCED_CUBES_SHADER: &str = r#"// Instanced Cubes Example
+// Slot 0 steps per vertex (the cube mesh), slot 1 per instance (one cube).
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    time: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+}
+
+// Advanced once per instance: VertexStepMode::Instance
+struct InstanceInput {
+    @location(2) offset: vec3<f32>,
+    @location(3) scale: f32,
+    @location(4) color: vec3<f32>,
+    @location(5) spin: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+}
+
+fn rotate_y(v: vec3<f32>, angle: f32) -> vec3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec3<f32>(c * v.x + s * v.z, v.y, -s * v.x + c * v.z);
+}
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    let angle = uniforms.time * instance.spin;
+    let world = rotate_y(vertex.position, angle) * instance.scale + instance.offset;
+    let normal = rotate_y(vertex.normal, angle);
+    let light = max(dot(normal, normalize(vec3<f32>(0.4, 0.8, 0.5))), 0.0);
+
+    var out: VertexOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(world, 1.0);
+    out.color = instance.color * (0.3 + 0.7 * light);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+"#;
+
+/// How the cubes are submitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawPath {
+    /// One draw call for all cubes, transforms from the instance buffer
+    Instanced,
+    /// One vertex buffer binding and one draw call per cube
+    PerObject,
+}
+
+impl DrawPath {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DrawPath::Instanced => "Instanced (1 draw)",
+            DrawPath::PerObject => "Naive (1 draw per cube)",
+        }
+    }
+}
+
+/// Vertex of the shared cube mesh
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct CubeVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+}
+
+/// Per-instance attributes, read through `VertexStepMode::Instance`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct CubeInstance {
+    pub offset: [f32; 3],
+    pub scale: f32,
+    pub color: [f32; 3],
+    /// Rotation speed around the cube's own Y axis in radians per second
+    pub spin: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: [[f32; 4]; 4],
+    time: f32,
+    _padding: [f32; 3],
+}
+
+/// A unit cube with per-face normals: 24 vertices and 36 counter-clockwise indices
+pub fn cube_mesh() -> (Vec<CubeVertex>, Vec<u16>) {
+    // Face normal and one in-plane axis; the other axis is normal x axis,
+    // which makes the corner order below counter-clockwise from outside
+    let faces: [([f32; 3], [f32; 3]); 6] = [
+        ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+        ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+        ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0]),
+        ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0]),
+        ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0]),
+        ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0]),
+    ];
+
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+    for (normal, u) in faces {
+        let v = cross(normal, u);
+        let base = vertices.len() as u16;
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = std::array::from_fn(|i| 0.5 * (normal[i] + su * u[i] + sv * v[i]));
+            vertices.push(CubeVertex { position, normal });
+        }
+        indices.extend([base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+    (vertices, indices)
+}
+
+/// Number of cubes along each side of the grid holding `count` cubes
+fn grid_side(count: u32) -> u32 {
+    let mut side = (count as f32).cbrt().ceil() as u32;
+    // Guard against cbrt rounding just below an exact cube
+    while side.pow(3) < count {
+        side += 1;
+    }
+    side.max(1)
+}
+
+/// `count` cubes filling a grid centered on the origin, colored by position
+pub fn instance_grid(count: u32) -> Vec<CubeInstance> {
+    let side = grid_side(count);
+    let center = (side - 1) as f32 / 2.0;
+    let max = (side - 1).max(1) as f32;
+    (0..count)
+        .map(|i| {
+            let cell = [i % side, (i / side) % side, i / (side * side)];
+            // Cheap deterministic variation of the spin speed
+            let hash = (i.wrapping_mul(2_654_435_761) % 1000) as f32 / 1000.0;
+            CubeInstance {
+                offset: cell.map(|c| (c as f32 - center) * SPACING),
+                scale: 1.0,
+                color: cell.map(|c| 0.2 + 0.8 * c as f32 / max),
+                spin: 0.5 + 2.0 * hash,
+            }
+        })
+        .collect()
+}
+
+/// Radius of a sphere around the origin containing a grid of `count` cubes
+pub fn grid_radius(count: u32) -> f32 {
+    let half_extent = (grid_side(count) as f32 * SPACING) / 2.0;
+    half_extent * 3.0_f32.sqrt()
+}
+
+/// Renderer of the instanced cubes example
+pub struct InstancedCubes {
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    instance_buffer: wgpu::Buffer,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    depth_view: wgpu::TextureView,
+    depth_size: (u32, u32),
+    instance_count: u32,
+    path: DrawPath,
+    /// Smoothed CPU time to record and submit a frame, per path
+    instanced_time: Option<Duration>,
+    per_object_time: Option<Duration>,
+}
+
+impl InstancedCubes {
+    /// Create the renderer drawing into `color_format` targets of `width`x`height`
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Instanced Cubes Shader"),
+            source: wgpu::ShaderSource::Wgsl(INSTANCED_CUBES_SHADER.into()),
+        });
+
+        let (vertices, indices) = cube_mesh();
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Instanced Cubes Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Instanced Cubes Index Buffer"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instanced Cubes Instance Buffer"),
+            size: MAX_INSTANCES as u64 * std::mem::size_of::<CubeInstance>() as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instanced Cubes Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Instanced Cubes Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Instanced Cubes Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Instanced Cubes Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Instanced Cubes Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<CubeVertex>() as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3],
+                    },
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<CubeInstance>() as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![
+                            2 => Float32x3,
+                            3 => Float32,
+                            4 => Float32x3,
+                            5 => Float32
+                        ],
+                    },
+                ],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        let mut cubes = Self {
+            pipeline,
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+            instance_buffer,
+            uniform_buffer,
+            bind_group,
+            depth_view: create_depth_view(device, width, height),
+            depth_size: (width, height),
+            instance_count: 0,
+            path: DrawPath::Instanced,
+            instanced_time: None,
+            per_object_time: None,
+        };
+        cubes.set_instance_count(queue, 1000);
+        cubes
+    }
+
+    /// Lay out `count` cubes, clamped to [`MAX_INSTANCES`]
+    pub fn set_instance_count(&mut self, queue: &wgpu::Queue, count: u32) {
+        let count = count.min(MAX_INSTANCES);
+        if count == self.instance_count {
+            return;
+        }
+        upload_belt::write_buffer(
+            queue,
+            &self.instance_buffer,
+            0,
+            bytemuck::cast_slice(&instance_grid(count)),
+        );
+        self.instance_count = count;
+    }
+
+    pub fn instance_count(&self) -> u32 {
+        self.instance_count
+    }
+
+    pub fn set_path(&mut self, path: DrawPath) {
+        self.path = path;
+    }
+
+    pub fn path(&self) -> DrawPath {
+        self.path
+    }
+
+    /// Draw calls issued per frame by the current path
+    pub fn draw_calls(&self) -> u32 {
+        match self.path {
+            DrawPath::Instanced => u32::from(self.instance_count > 0),
+            DrawPath::PerObject => self.instance_count,
+        }
+    }
+
+    /// Smoothed CPU time to record and submit a frame with `path`, once
+    /// a frame was drawn that way
+    pub fn frame_time(&self, path: DrawPath) -> Option<Duration> {
+        match path {
+            DrawPath::Instanced => self.instanced_time,
+            DrawPath::PerObject => self.per_object_time,
+        }
+    }
+
+    /// Update the camera and animation time
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], time: f32) {
+        let uniforms = Uniforms {
+            view_proj,
+            time,
+            _padding: [0.0; 3],
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+    }
+
+    /// Draw the cubes into `target`, a view of a texture of the given size
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        if self.depth_size != (width, height) {
+            self.depth_view = create_depth_view(device, width, height);
+            self.depth_size = (width, height);
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let start = Instant::now();
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Instanced Cubes Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Instanced Cubes Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+
+            let stride = std::mem::size_of::<CubeInstance>() as u64;
+            match self.path {
+                DrawPath::Instanced => {
+                    tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+                    render_pass.set_vertex_buffer(
+                        1,
+                        self.instance_buffer
+                            .slice(..self.instance_count as u64 * stride),
+                    );
+                    tracker.record(ApiCategory::RenderPass, "draw_indexed");
+                    render_pass.draw_indexed(0..self.index_count, 0, 0..self.instance_count);
+                }
+                DrawPath::PerObject => {
+                    tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+                    tracker.record(ApiCategory::RenderPass, "draw_indexed");
+                    for i in 0..self.instance_count as u64 {
+                        render_pass.set_vertex_buffer(
+                            1,
+                            self.instance_buffer.slice(i * stride..(i + 1) * stride),
+                        );
+                        render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+                    }
+                }
+            }
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+
+        let elapsed = start.elapsed();
+        let smoothed = match self.path {
+            DrawPath::Instanced => &mut self.instanced_time,
+            DrawPath::PerObject => &mut self.per_object_time,
+        };
+        *smoothed = Some(match *smoothed {
+            Some(previous) => previous.mul_f32(1.0 - SMOOTHING) + elapsed.mul_f32(SMOOTHING),
+            None => elapsed,
+        });
+    }
+}
+
+fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Instanced Cubes Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_utils::dot;
+
+    #[test]
+    fn test_struct_sizes_match_vertex_layouts() {
+        assert_eq!(std::mem::size_of::<CubeVertex>(), 24);
+        assert_eq!(std::mem::size_of::<CubeInstance>(), 32);
+        assert_eq!(std::mem::size_of::<Uniforms>(), 80);
+    }
+
+    #[test]
+    fn test_cube_mesh_winds_counter_clockwise_from_outside() {
+        let (vertices, indices) = cube_mesh();
+        assert_eq!(vertices.len(), 24);
+        assert_eq!(indices.len(), 36);
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| vertices[triangle[i] as usize]);
+            let ab = std::array::from_fn(|i| b.position[i] - a.position[i]);
+            let ac = std::array::from_fn(|i| c.position[i] - a.position[i]);
+            assert!(dot(cross(ab, ac), a.normal) > 0.0);
+            // Every corner of a face lies on the face's plane
+            assert!((dot(a.position, a.normal) - 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_instance_grid_is_centered_and_unique() {
+        for count in [1, 8, 9, 1000, 1001] {
+            let grid = instance_grid(count);
+            assert_eq!(grid.len(), count as usize);
+            for (i, a) in grid.iter().enumerate() {
+                assert!(a.offset.iter().all(|c| c.abs() <= grid_radius(count)));
+                assert!(grid[i + 1..].iter().all(|b| a.offset != b.offset));
+            }
+        }
+        assert_eq!(instance_grid(8)[0].offset, [-0.8, -0.8, -0.8]);
+        assert_eq!(instance_grid(1)[0].offset, [0.0, 0.0, 0.0]);
+        assert_eq!(grid_side(27), 3);
+        assert_eq!(grid_side(28), 4);
+    }
+
+    #[test]
+    fn test_draw_path_labels() {
+        assert_ne!(DrawPath::Instanced.label(), DrawPath::PerObject.label());
+    }
+}
@@ -0,0 +1,375 @@
+//! Background jobs for work too slow for the UI thread
+//!
+//! Decoding a large image or parsing a glTF file takes long enough to drop
+//! several frames when done inside the egui frame. [`JobSystem`] runs such
+//! work on a small pool of worker threads fed through a channel. Spawning
+//! returns a [`Job`], which the panel that asked for it polls once per
+//! frame; the result arrives over the job's own channel. Jobs report what
+//! they are doing through a [`JobProgress`], and [`JobSystem::active`] lists
+//! the unfinished ones so the GUI can show them.
+//!
+//! On the web there are no threads, so jobs run as soon as they are spawned
+//! and are already finished when first polled.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+
+/// Most worker threads the global job system starts
+pub const MAX_WORKERS: usize = 4;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// Why a job produced no result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobError {
+    /// The job was cancelled before it finished
+    Cancelled,
+    /// The job panicked
+    Panicked,
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobError::Cancelled => write!(f, "Job was cancelled"),
+            JobError::Panicked => write!(f, "Job panicked"),
+        }
+    }
+}
+
+impl std::error::Error for JobError {}
+
+/// Snapshot of a job's progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobStatus {
+    /// Name given when the job was spawned
+    pub label: String,
+    /// What the job is doing, empty while queued
+    pub stage: String,
+    /// Fraction of the work done, from 0 to 1
+    pub fraction: f32,
+    /// Whether a worker has picked the job up
+    pub running: bool,
+}
+
+#[derive(Debug)]
+struct ProgressState {
+    status: Mutex<JobStatus>,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// Handle a job reports its progress through and checks for cancellation
+#[derive(Debug, Clone)]
+pub struct JobProgress {
+    state: Arc<ProgressState>,
+}
+
+impl JobProgress {
+    fn new(label: &str) -> Self {
+        Self {
+            state: Arc::new(ProgressState {
+                status: Mutex::new(JobStatus {
+                    label: label.to_string(),
+                    stage: String::new(),
+                    fraction: 0.0,
+                    running: false,
+                }),
+                cancelled: AtomicBool::new(false),
+                finished: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, JobStatus> {
+        self.state.status.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start a new stage, `fraction` of the way through the job
+    pub fn set_stage(&self, stage: &str, fraction: f32) {
+        let mut status = self.lock();
+        status.stage = stage.to_string();
+        status.fraction = fraction.clamp(0.0, 1.0);
+    }
+
+    /// Report how much of the job is done, from 0 to 1
+    pub fn set_fraction(&self, fraction: f32) {
+        self.lock().fraction = fraction.clamp(0.0, 1.0);
+    }
+
+    /// Whether the job's result is no longer wanted
+    ///
+    /// Long jobs should check this between steps and return early.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn status(&self) -> JobStatus {
+        self.lock().clone()
+    }
+
+    fn start(&self) {
+        self.lock().running = true;
+    }
+
+    fn finish(&self) {
+        self.state.finished.store(true, Ordering::Release);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+}
+
+/// A job spawned on a [`JobSystem`], producing a `T`
+///
+/// Dropping the job cancels it.
+pub struct Job<T> {
+    receiver: mpsc::Receiver<T>,
+    progress: JobProgress,
+    /// Whether `poll` has returned the outcome
+    taken: bool,
+}
+
+impl<T> Job<T> {
+    /// The result, once the job has finished
+    ///
+    /// Returns `None` while the job is queued or running, and again after
+    /// the outcome has been returned once.
+    pub fn poll(&mut self) -> Option<Result<T, JobError>> {
+        if self.taken {
+            return None;
+        }
+        let outcome = match self.receiver.try_recv() {
+            Ok(value) => Ok(value),
+            Err(mpsc::TryRecvError::Empty) => return None,
+            Err(mpsc::TryRecvError::Disconnected) => Err(self.failure()),
+        };
+        self.taken = true;
+        Some(outcome)
+    }
+
+    /// Block until the job has finished
+    pub fn wait(self) -> Result<T, JobError> {
+        self.receiver.recv().map_err(|_| self.failure())
+    }
+
+    /// Ask the job to stop; a queued job never runs
+    pub fn cancel(&self) {
+        self.progress.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn status(&self) -> JobStatus {
+        self.progress.status()
+    }
+
+    fn failure(&self) -> JobError {
+        if self.progress.is_cancelled() {
+            JobError::Cancelled
+        } else {
+            JobError::Panicked
+        }
+    }
+}
+
+impl<T> Drop for Job<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl<T> fmt::Debug for Job<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Job")
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+/// Pool of worker threads running [`Job`]s in the order they were spawned
+pub struct JobSystem {
+    sender: Mutex<mpsc::Sender<Task>>,
+    workers: usize,
+    /// Progress of every job not yet finished
+    active: Mutex<Vec<JobProgress>>,
+}
+
+impl JobSystem {
+    /// Start a job system with `workers` threads, at least one
+    pub fn new(workers: usize) -> Self {
+        let workers = workers.max(1);
+        let (sender, receiver) = mpsc::channel::<Task>();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let receiver = Arc::new(Mutex::new(receiver));
+            for index in 0..workers {
+                let receiver = Arc::clone(&receiver);
+                std::thread::Builder::new()
+                    .name(format!("playground-job-{}", index))
+                    .spawn(move || loop {
+                        // The lock is released before the task runs
+                        let task = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                        match task {
+                            Ok(task) => task(),
+                            Err(_) => break,
+                        }
+                    })
+                    .expect("Failed to start a job worker thread");
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        drop(receiver);
+        Self {
+            sender: Mutex::new(sender),
+            workers,
+            active: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The job system shared by the whole playground
+    ///
+    /// Leaves a core for the UI thread, with at most [`MAX_WORKERS`]
+    /// workers.
+    pub fn global() -> &'static JobSystem {
+        static GLOBAL: OnceLock<JobSystem> = OnceLock::new();
+        GLOBAL.get_or_init(|| {
+            let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+            JobSystem::new((cores.saturating_sub(1)).clamp(1, MAX_WORKERS))
+        })
+    }
+
+    /// Number of worker threads
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Run `work` on a worker thread
+    ///
+    /// `label` names the job in [`active`](Self::active). A panic in `work`
+    /// is reported by the job as [`JobError::Panicked`] and leaves the
+    /// worker running.
+    pub fn spawn<T, F>(&self, label: &str, work: F) -> Job<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&JobProgress) -> T + Send + 'static,
+    {
+        let progress = JobProgress::new(label);
+        let (sender, receiver) = mpsc::channel();
+        let task_progress = progress.clone();
+        let task: Task = Box::new(move || {
+            let progress = task_progress;
+            if progress.is_cancelled() {
+                progress.finish();
+                return;
+            }
+            progress.start();
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| work(&progress)));
+            // Finished before the result is sent, so a job whose result has
+            // arrived is never listed as active
+            progress.finish();
+            match result {
+                Ok(value) => {
+                    let _ = sender.send(value);
+                }
+                Err(_) => log::error!("Job '{}' panicked", progress.status().label),
+            }
+        });
+
+        {
+            let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+            active.retain(|job| !job.is_finished());
+            active.push(progress.clone());
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let sender = self.sender.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(mpsc::SendError(task)) = sender.send(task) {
+                // No worker is left to run it
+                drop(task);
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = &self.sender;
+            task();
+        }
+        Job {
+            receiver,
+            progress,
+            taken: false,
+        }
+    }
+
+    /// Status of every job that is queued or running
+    pub fn active(&self) -> Vec<JobStatus> {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        active.retain(|job| !job.is_finished());
+        active.iter().map(JobProgress::status).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_spawn_returns_the_result() {
+        let jobs = JobSystem::new(2);
+        let job = jobs.spawn("sum", |_| (1..=10).sum::<u32>());
+        assert_eq!(job.wait(), Ok(55));
+    }
+
+    #[test]
+    fn test_progress_and_active_jobs() {
+        let jobs = JobSystem::new(1);
+        let (release, blocked) = mpsc::channel::<()>();
+        let mut job = jobs.spawn("blocking", move |progress| {
+            progress.set_stage("Waiting", 0.5);
+            blocked.recv().unwrap();
+            7
+        });
+        let queued = jobs.spawn("queued", |_| 8);
+
+        while job.status().stage.is_empty() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let status = jobs.active();
+        assert_eq!(status.len(), 2);
+        assert_eq!(status[0].stage, "Waiting");
+        assert_eq!(status[0].fraction, 0.5);
+        assert!(status[0].running);
+        assert!(!status[1].running);
+        assert!(job.poll().is_none());
+
+        release.send(()).unwrap();
+        assert_eq!(job.wait(), Ok(7));
+        assert_eq!(queued.wait(), Ok(8));
+        assert!(jobs.active().is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_job_never_runs() {
+        let jobs = JobSystem::new(1);
+        let (release, blocked) = mpsc::channel::<()>();
+        let first = jobs.spawn("blocking", move |_| blocked.recv().unwrap());
+        let ran = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&ran);
+        let second = jobs.spawn("cancelled", move |_| flag.store(true, Ordering::SeqCst));
+        second.cancel();
+
+        release.send(()).unwrap();
+        first.wait().unwrap();
+        assert_eq!(second.wait(), Err(JobError::Cancelled));
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_panicking_job_keeps_the_worker() {
+        let jobs = JobSystem::new(1);
+        let job = jobs.spawn("panics", |_| -> u32 { panic!("job failure") });
+        assert_eq!(job.wait(), Err(JobError::Panicked));
+        assert_eq!(jobs.spawn("after", |_| 3).wait(), Ok(3));
+    }
+}
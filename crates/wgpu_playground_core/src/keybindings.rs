@@ -0,0 +1,670 @@
+//! Keyboard shortcuts of the playground's actions
+//!
+//! Every action that can be triggered from the keyboard is an [`Action`];
+//! [`KeyBindings`] maps each one to the [`Shortcut`]s that trigger it. The
+//! GUI asks the bindings which actions the current frame's key presses
+//! trigger instead of checking keys itself, so remapping an action in the
+//! Keyboard Shortcuts tab changes it everywhere. Bindings that differ from
+//! the defaults are saved as JSON in the user's config directory.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Environment variable overriding the directory user settings are saved in
+pub const CONFIG_DIR_ENV: &str = "WGPU_PLAYGROUND_CONFIG";
+
+/// File name of the key bindings in the config directory
+pub const KEYBINDINGS_FILE: &str = "keybindings.json";
+
+/// Something the user can trigger with a keyboard shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    NewProject,
+    SaveState,
+    LoadState,
+    Undo,
+    Redo,
+    CompileShader,
+    RunDispatch,
+    Screenshot,
+    CaptureFrame,
+    TogglePreview,
+    CommandPalette,
+    ShowRendering,
+    ShowCompute,
+    ShowBuffers,
+    ShowTextures,
+    ShowConsole,
+    ShowSettings,
+}
+
+impl Action {
+    /// All actions, in the order the settings page lists them
+    pub const ALL: [Action; 17] = [
+        Action::NewProject,
+        Action::SaveState,
+        Action::LoadState,
+        Action::Undo,
+        Action::Redo,
+        Action::CompileShader,
+        Action::RunDispatch,
+        Action::Screenshot,
+        Action::CaptureFrame,
+        Action::TogglePreview,
+        Action::CommandPalette,
+        Action::ShowRendering,
+        Action::ShowCompute,
+        Action::ShowBuffers,
+        Action::ShowTextures,
+        Action::ShowConsole,
+        Action::ShowSettings,
+    ];
+
+    /// Identifier used in the saved bindings and message keys
+    pub fn id(&self) -> &'static str {
+        match self {
+            Action::NewProject => "new_project",
+            Action::SaveState => "save_state",
+            Action::LoadState => "load_state",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::CompileShader => "compile_shader",
+            Action::RunDispatch => "run_dispatch",
+            Action::Screenshot => "screenshot",
+            Action::CaptureFrame => "capture_frame",
+            Action::TogglePreview => "toggle_preview",
+            Action::CommandPalette => "command_palette",
+            Action::ShowRendering => "show_rendering",
+            Action::ShowCompute => "show_compute",
+            Action::ShowBuffers => "show_buffers",
+            Action::ShowTextures => "show_textures",
+            Action::ShowConsole => "show_console",
+            Action::ShowSettings => "show_settings",
+        }
+    }
+
+    /// Action with the identifier `id`
+    pub fn from_id(id: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    /// Display name in the current language
+    pub fn name(&self) -> String {
+        crate::i18n::tr(match self {
+            Action::NewProject => "keybindings-action-new-project",
+            Action::SaveState => "keybindings-action-save-state",
+            Action::LoadState => "keybindings-action-load-state",
+            Action::Undo => "keybindings-action-undo",
+            Action::Redo => "keybindings-action-redo",
+            Action::CompileShader => "keybindings-action-compile-shader",
+            Action::RunDispatch => "keybindings-action-run-dispatch",
+            Action::Screenshot => "keybindings-action-screenshot",
+            Action::CaptureFrame => "keybindings-action-capture-frame",
+            Action::TogglePreview => "keybindings-action-toggle-preview",
+            Action::CommandPalette => "keybindings-action-command-palette",
+            Action::ShowRendering => "keybindings-action-show-rendering",
+            Action::ShowCompute => "keybindings-action-show-compute",
+            Action::ShowBuffers => "keybindings-action-show-buffers",
+            Action::ShowTextures => "keybindings-action-show-textures",
+            Action::ShowConsole => "keybindings-action-show-console",
+            Action::ShowSettings => "keybindings-action-show-settings",
+        })
+    }
+
+    /// Whether the action is ignored while a text field has focus, because
+    /// text fields handle the same keys themselves
+    pub fn yields_to_text_input(&self) -> bool {
+        matches!(self, Action::Undo | Action::Redo)
+    }
+
+    /// Shortcuts the action has out of the box
+    pub fn default_shortcuts(&self) -> Vec<Shortcut> {
+        use egui::Key;
+        match self {
+            Action::NewProject => vec![Shortcut::command(Key::N)],
+            Action::SaveState => vec![Shortcut::command(Key::S)],
+            Action::LoadState => vec![Shortcut::command(Key::O)],
+            Action::Undo => vec![Shortcut::command(Key::Z)],
+            Action::Redo => vec![
+                Shortcut::command(Key::Y),
+                Shortcut::command(Key::Z).with_shift(),
+            ],
+            Action::CompileShader => vec![Shortcut::key(Key::F5)],
+            Action::RunDispatch => vec![Shortcut::key(Key::F6)],
+            Action::Screenshot => vec![Shortcut::key(Key::F12)],
+            Action::CaptureFrame => vec![Shortcut::command(Key::F12)],
+            Action::TogglePreview => vec![Shortcut::key(Key::F7)],
+            Action::CommandPalette => vec![Shortcut::command(Key::P)],
+            Action::ShowRendering => vec![Shortcut::command(Key::Num1)],
+            Action::ShowCompute => vec![Shortcut::command(Key::Num2)],
+            Action::ShowBuffers => vec![Shortcut::command(Key::Num3)],
+            Action::ShowTextures => vec![Shortcut::command(Key::Num4)],
+            Action::ShowConsole => vec![Shortcut::command(Key::Num5)],
+            Action::ShowSettings => vec![Shortcut::command(Key::Num6)],
+        }
+    }
+}
+
+/// A key together with the modifiers that must be held
+///
+/// `command` is Ctrl, or Cmd on macOS. Written as text like `Ctrl+Shift+Z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Shortcut {
+    pub key: egui::Key,
+    pub command: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Shortcut {
+    /// `key` without modifiers
+    pub const fn key(key: egui::Key) -> Self {
+        Self {
+            key,
+            command: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// Ctrl/Cmd+`key`
+    pub const fn command(key: egui::Key) -> Self {
+        Self {
+            key,
+            command: true,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// The shortcut with Shift held as well
+    pub const fn with_shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// The shortcut with Alt held as well
+    pub const fn with_alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Shortcut of a key press with `modifiers`
+    pub fn from_press(key: egui::Key, modifiers: egui::Modifiers) -> Self {
+        Self {
+            key,
+            command: modifiers.command,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+        }
+    }
+
+    /// Whether a press of `key` with exactly `modifiers` held triggers the
+    /// shortcut
+    pub fn matches(&self, key: egui::Key, modifiers: egui::Modifiers) -> bool {
+        *self == Self::from_press(key, modifiers)
+    }
+
+    /// What egui or text fields already do on this shortcut, if anything
+    pub fn reserved_by(&self) -> Option<String> {
+        use egui::Key;
+        if self.alt || !self.command {
+            return None;
+        }
+        match (self.key, self.shift) {
+            (Key::C | Key::X | Key::V | Key::A, false) => {
+                Some(crate::i18n::tr("keybindings-reserved-text-editing"))
+            }
+            (Key::Plus | Key::Equals | Key::Minus | Key::Num0, _) => {
+                Some(crate::i18n::tr("keybindings-reserved-zoom"))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.command {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key.name())
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let Some(key_name) = parts.pop().filter(|name| !name.is_empty()) else {
+            return Err(format!("Shortcut '{}' has no key", s));
+        };
+        let key = egui::Key::from_name(key_name)
+            .ok_or_else(|| format!("Unknown key '{}' in shortcut '{}'", key_name, s))?;
+        let mut shortcut = Shortcut::key(key);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => shortcut.command = true,
+                "shift" => shortcut.shift = true,
+                "alt" | "option" => shortcut.alt = true,
+                _ => {
+                    return Err(format!(
+                        "Unknown modifier '{}' in shortcut '{}'",
+                        modifier, s
+                    ))
+                }
+            }
+        }
+        Ok(shortcut)
+    }
+}
+
+impl TryFrom<String> for Shortcut {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Shortcut> for String {
+    fn from(shortcut: Shortcut) -> Self {
+        shortcut.to_string()
+    }
+}
+
+/// Error loading or saving key bindings
+#[derive(Debug)]
+pub enum KeyBindingsError {
+    /// No config directory could be determined
+    NoConfigDir,
+    /// Reading or writing the file failed
+    Io(io::Error),
+    /// The file is not valid JSON
+    Parse(serde_json::Error),
+}
+
+impl fmt::Display for KeyBindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyBindingsError::NoConfigDir => write!(
+                f,
+                "No config directory found; set {} to choose one",
+                CONFIG_DIR_ENV
+            ),
+            KeyBindingsError::Io(e) => write!(f, "Key bindings file error: {}", e),
+            KeyBindingsError::Parse(e) => write!(f, "Invalid key bindings file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for KeyBindingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeyBindingsError::NoConfigDir => None,
+            KeyBindingsError::Io(e) => Some(e),
+            KeyBindingsError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for KeyBindingsError {
+    fn from(e: io::Error) -> Self {
+        KeyBindingsError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for KeyBindingsError {
+    fn from(e: serde_json::Error) -> Self {
+        KeyBindingsError::Parse(e)
+    }
+}
+
+/// Contents of the key bindings file
+///
+/// Only actions whose shortcuts differ from the defaults are written, so
+/// actions added later get their default shortcuts. Actions and shortcuts
+/// are kept as text so that one unknown entry doesn't discard the rest.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct KeyBindingsFile {
+    #[serde(default)]
+    bindings: BTreeMap<String, Vec<String>>,
+}
+
+/// The user config directory of the platform
+fn platform_config_dir() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    #[cfg(target_os = "windows")]
+    let base = var("APPDATA").map(PathBuf::from);
+    #[cfg(target_os = "macos")]
+    let base = var("HOME").map(|home| {
+        PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+    });
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let base = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")));
+    base.map(|dir| dir.join("wgpu_playground"))
+}
+
+/// Directory of user settings such as the key bindings
+///
+/// [`CONFIG_DIR_ENV`] if it is set, otherwise the platform's config
+/// directory.
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os(CONFIG_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(platform_config_dir)
+}
+
+/// Where the key bindings are saved
+pub fn default_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(KEYBINDINGS_FILE))
+}
+
+/// Shortcuts of every action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: BTreeMap<Action, Vec<Shortcut>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyBindings {
+    /// Bindings with every action on its default shortcuts
+    pub fn new() -> Self {
+        Self {
+            bindings: Action::ALL
+                .into_iter()
+                .map(|action| (action, action.default_shortcuts()))
+                .collect(),
+        }
+    }
+
+    /// Shortcuts triggering `action`
+    pub fn shortcuts(&self, action: Action) -> &[Shortcut] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// First shortcut of `action` as text, for menus and tooltips
+    pub fn label(&self, action: Action) -> Option<String> {
+        self.shortcuts(action).first().map(Shortcut::to_string)
+    }
+
+    /// Replace the shortcuts of `action`
+    pub fn set(&mut self, action: Action, shortcuts: Vec<Shortcut>) {
+        let mut unique = Vec::with_capacity(shortcuts.len());
+        for shortcut in shortcuts {
+            if !unique.contains(&shortcut) {
+                unique.push(shortcut);
+            }
+        }
+        self.bindings.insert(action, unique);
+    }
+
+    /// Add `shortcut` to the shortcuts of `action`
+    pub fn add(&mut self, action: Action, shortcut: Shortcut) {
+        let shortcuts = self.bindings.entry(action).or_default();
+        if !shortcuts.contains(&shortcut) {
+            shortcuts.push(shortcut);
+        }
+    }
+
+    /// Remove `shortcut` from the shortcuts of `action`
+    pub fn remove(&mut self, action: Action, shortcut: Shortcut) {
+        if let Some(shortcuts) = self.bindings.get_mut(&action) {
+            shortcuts.retain(|bound| *bound != shortcut);
+        }
+    }
+
+    /// Put `action` back on its default shortcuts
+    pub fn reset(&mut self, action: Action) {
+        self.bindings.insert(action, action.default_shortcuts());
+    }
+
+    /// Put every action back on its default shortcuts
+    pub fn reset_all(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Whether `action` has its default shortcuts
+    pub fn is_default(&self, action: Action) -> bool {
+        self.shortcuts(action) == action.default_shortcuts().as_slice()
+    }
+
+    /// Actions bound to `shortcut`
+    pub fn actions_for(&self, shortcut: Shortcut) -> Vec<Action> {
+        self.bindings
+            .iter()
+            .filter(|(_, shortcuts)| shortcuts.contains(&shortcut))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+
+    /// Shortcuts bound to more than one action, with those actions
+    pub fn conflicts(&self) -> Vec<(Shortcut, Vec<Action>)> {
+        let mut conflicts: Vec<(Shortcut, Vec<Action>)> = Vec::new();
+        for shortcuts in self.bindings.values() {
+            for shortcut in shortcuts {
+                if conflicts.iter().any(|(seen, _)| seen == shortcut) {
+                    continue;
+                }
+                let actions = self.actions_for(*shortcut);
+                if actions.len() > 1 {
+                    conflicts.push((*shortcut, actions));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Actions triggered by the key presses of this frame
+    ///
+    /// Modifiers must match exactly, so Ctrl+Shift+Z doesn't also trigger
+    /// Ctrl+Z. Key repeats don't trigger actions again.
+    pub fn triggered(&self, input: &egui::InputState) -> Vec<Action> {
+        let mut actions = Vec::new();
+        for event in &input.events {
+            let egui::Event::Key {
+                key,
+                pressed: true,
+                repeat: false,
+                modifiers,
+                ..
+            } = event
+            else {
+                continue;
+            };
+            for (action, shortcuts) in &self.bindings {
+                if shortcuts.iter().any(|s| s.matches(*key, *modifiers))
+                    && !actions.contains(action)
+                {
+                    actions.push(*action);
+                }
+            }
+        }
+        actions
+    }
+
+    /// Bindings from JSON, with the defaults for actions it leaves out
+    ///
+    /// Unknown actions and shortcuts are skipped with a warning.
+    pub fn from_json(json: &str) -> Result<Self, KeyBindingsError> {
+        let file: KeyBindingsFile = serde_json::from_str(json)?;
+        let mut bindings = Self::new();
+        for (id, texts) in file.bindings {
+            let Some(action) = Action::from_id(&id) else {
+                log::warn!("Ignoring key bindings of unknown action '{}'", id);
+                continue;
+            };
+            let shortcuts = texts
+                .iter()
+                .filter_map(|text| match text.parse() {
+                    Ok(shortcut) => Some(shortcut),
+                    Err(e) => {
+                        log::warn!("Ignoring shortcut of {}: {}", id, e);
+                        None
+                    }
+                })
+                .collect();
+            bindings.set(action, shortcuts);
+        }
+        Ok(bindings)
+    }
+
+    /// JSON of the bindings that differ from the defaults
+    pub fn to_json(&self) -> Result<String, KeyBindingsError> {
+        let file = KeyBindingsFile {
+            bindings: self
+                .bindings
+                .iter()
+                .filter(|(action, _)| !self.is_default(**action))
+                .map(|(action, shortcuts)| {
+                    (
+                        action.id().to_string(),
+                        shortcuts.iter().map(Shortcut::to_string).collect(),
+                    )
+                })
+                .collect(),
+        };
+        Ok(serde_json::to_string_pretty(&file)?)
+    }
+
+    /// Load bindings from `path`, the defaults if it doesn't exist
+    pub fn load_from(path: &Path) -> Result<Self, KeyBindingsError> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Save the bindings to `path`, creating its directory
+    pub fn save_to(&self, path: &Path) -> Result<(), KeyBindingsError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Load the bindings saved in the config directory
+    pub fn load() -> Result<Self, KeyBindingsError> {
+        Self::load_from(&default_path().ok_or(KeyBindingsError::NoConfigDir)?)
+    }
+
+    /// Save the bindings to the config directory
+    pub fn save(&self) -> Result<(), KeyBindingsError> {
+        self.save_to(&default_path().ok_or(KeyBindingsError::NoConfigDir)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Key;
+
+    #[test]
+    fn test_shortcut_text_round_trip() {
+        for text in ["F5", "Ctrl+S", "Ctrl+Shift+Z", "Ctrl+Alt+1", "Ctrl+Plus"] {
+            let shortcut: Shortcut = text.parse().unwrap();
+            assert_eq!(shortcut.to_string(), text);
+        }
+        assert_eq!(
+            "cmd+shift+z".parse::<Shortcut>().unwrap(),
+            Shortcut::command(Key::Z).with_shift()
+        );
+        assert!("Ctrl+".parse::<Shortcut>().is_err());
+        assert!("Hyper+A".parse::<Shortcut>().is_err());
+        assert!("Ctrl+NoSuchKey".parse::<Shortcut>().is_err());
+    }
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        let bindings = KeyBindings::new();
+        assert!(bindings.conflicts().is_empty());
+        for action in Action::ALL {
+            assert!(bindings.is_default(action));
+            assert!(!bindings.shortcuts(action).is_empty(), "{:?}", action);
+            assert_eq!(Action::from_id(action.id()), Some(action));
+        }
+    }
+
+    #[test]
+    fn test_conflict_detection() {
+        let mut bindings = KeyBindings::new();
+        bindings.add(Action::CompileShader, Shortcut::command(Key::S));
+        let conflicts = bindings.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0, Shortcut::command(Key::S));
+        assert_eq!(
+            conflicts[0].1,
+            vec![Action::SaveState, Action::CompileShader]
+        );
+
+        bindings.remove(Action::SaveState, Shortcut::command(Key::S));
+        assert!(bindings.conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_exact_modifier_match() {
+        let redo = Shortcut::command(Key::Z).with_shift();
+        let ctrl_shift = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+        assert!(redo.matches(Key::Z, ctrl_shift));
+        assert!(!Shortcut::command(Key::Z).matches(Key::Z, ctrl_shift));
+        assert!(!Shortcut::key(Key::F5).matches(Key::F5, egui::Modifiers::COMMAND));
+    }
+
+    #[test]
+    fn test_json_keeps_only_changes() {
+        let mut bindings = KeyBindings::new();
+        assert_eq!(
+            bindings.to_json().unwrap().replace(char::is_whitespace, ""),
+            r#"{"bindings":{}}"#
+        );
+
+        bindings.set(
+            Action::TogglePreview,
+            vec![Shortcut::command(Key::P).with_alt()],
+        );
+        let json = bindings.to_json().unwrap();
+        assert!(json.contains("toggle_preview"));
+        assert!(json.contains("Ctrl+Alt+P"));
+        assert!(!json.contains("save_state"));
+        assert_eq!(KeyBindings::from_json(&json).unwrap(), bindings);
+    }
+
+    #[test]
+    fn test_from_json_skips_unknown_entries() {
+        let bindings = KeyBindings::from_json(
+            r#"{"bindings":{"warp_drive":["F1"],"undo":["Ctrl+U","Nope+U"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            bindings.shortcuts(Action::Undo),
+            &[Shortcut::command(Key::U)]
+        );
+        assert!(bindings.is_default(Action::Redo));
+        assert!(KeyBindings::from_json("not json").is_err());
+    }
+}
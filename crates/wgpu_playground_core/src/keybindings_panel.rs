@@ -0,0 +1,286 @@
+use crate::command_palette::{Command, CommandKind, CommandRegistry};
+use crate::i18n::{tr, tr_args};
+use crate::keybindings::{self, Action, KeyBindings, Shortcut, CONFIG_DIR_ENV};
+
+/// Settings page for remapping the keyboard shortcuts
+pub struct KeyBindingsPanel {
+    bindings: KeyBindings,
+    /// Action waiting for the key press of a new shortcut
+    capturing: Option<Action>,
+    /// Result of the last load or save
+    message: Option<String>,
+}
+
+impl Default for KeyBindingsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyBindingsPanel {
+    /// Create the panel with the bindings saved in the config directory
+    ///
+    /// Falls back to the default bindings if they can't be loaded.
+    pub fn new() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let (bindings, message) = match KeyBindings::load() {
+            Ok(bindings) => (bindings, None),
+            Err(e) => {
+                log::warn!("Failed to load key bindings: {}", e);
+                (
+                    KeyBindings::new(),
+                    Some(tr_args("keybindings-load-failed", &[("error", &e)])),
+                )
+            }
+        };
+        #[cfg(target_arch = "wasm32")]
+        let (bindings, message) = (KeyBindings::new(), None);
+        Self::with_bindings(bindings, message)
+    }
+
+    fn with_bindings(bindings: KeyBindings, message: Option<String>) -> Self {
+        Self {
+            bindings,
+            capturing: None,
+            message,
+        }
+    }
+
+    /// Current bindings
+    pub fn bindings(&self) -> &KeyBindings {
+        &self.bindings
+    }
+
+    /// Whether the next key press is recorded as a shortcut rather than
+    /// triggering an action
+    pub fn is_capturing(&self) -> bool {
+        self.capturing.is_some()
+    }
+
+    /// Add the actions to the command palette with their shortcuts
+    ///
+    /// Actions that only switch panels are left to the panel commands, and
+    /// the palette doesn't list itself.
+    pub fn register_commands(&self, registry: &mut CommandRegistry) {
+        for action in Action::ALL {
+            if matches!(
+                action,
+                Action::CommandPalette
+                    | Action::ShowRendering
+                    | Action::ShowCompute
+                    | Action::ShowBuffers
+                    | Action::ShowTextures
+                    | Action::ShowConsole
+                    | Action::ShowSettings
+            ) {
+                continue;
+            }
+            registry.register(
+                Command::new(CommandKind::Action(action), action.name())
+                    .with_keywords([action.id().replace('_', " ")])
+                    .with_shortcut(self.bindings.label(action)),
+            );
+        }
+    }
+
+    /// Save the bindings after a change, reporting the outcome
+    fn save(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.message = Some(match self.bindings.save() {
+                Ok(()) => tr_args(
+                    "keybindings-saved",
+                    &[(
+                        "path",
+                        &keybindings::default_path().unwrap_or_default().display(),
+                    )],
+                ),
+                Err(e) => tr_args("keybindings-save-failed", &[("error", &e)]),
+            });
+        }
+    }
+
+    /// Record the first key press of this frame as a shortcut of the action
+    /// being captured; Escape cancels
+    fn capture(&mut self, ui: &egui::Ui) {
+        let Some(action) = self.capturing else {
+            return;
+        };
+        let pressed = ui.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => Some(Shortcut::from_press(*key, *modifiers)),
+                _ => None,
+            })
+        });
+        let Some(shortcut) = pressed else {
+            return;
+        };
+        self.capturing = None;
+        if shortcut != Shortcut::key(egui::Key::Escape) {
+            self.bindings.add(action, shortcut);
+            self.save();
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.capture(ui);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading(tr("keybindings-heading"));
+            ui.label(tr("keybindings-help"));
+            ui.add_space(5.0);
+
+            let conflicts = self.bindings.conflicts();
+            if !conflicts.is_empty() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 200, 100),
+                    tr_args("keybindings-conflicts", &[("count", &conflicts.len())]),
+                );
+                ui.add_space(5.0);
+            }
+
+            let mut remove = None;
+            let mut reset = None;
+            egui::Grid::new("keybindings_grid")
+                .num_columns(3)
+                .striped(true)
+                .spacing([20.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new(tr("keybindings-column-action")).strong());
+                    ui.label(egui::RichText::new(tr("keybindings-column-shortcuts")).strong());
+                    ui.label("");
+                    ui.end_row();
+
+                    for action in Action::ALL {
+                        ui.label(action.name());
+                        ui.horizontal_wrapped(|ui| {
+                            let shortcuts = self.bindings.shortcuts(action);
+                            if shortcuts.is_empty() {
+                                ui.weak(tr("keybindings-none"));
+                            }
+                            for &shortcut in shortcuts {
+                                let others: Vec<String> = self
+                                    .bindings
+                                    .actions_for(shortcut)
+                                    .into_iter()
+                                    .filter(|other| *other != action)
+                                    .map(|other| other.name())
+                                    .collect();
+                                let mut text =
+                                    egui::RichText::new(shortcut.to_string()).monospace();
+                                let mut hover = vec![tr("keybindings-remove-tooltip")];
+                                if !others.is_empty() {
+                                    text = text.color(egui::Color32::from_rgb(220, 80, 80));
+                                    hover.push(tr_args(
+                                        "keybindings-conflict",
+                                        &[("actions", &others.join(", "))],
+                                    ));
+                                } else if let Some(use_) = shortcut.reserved_by() {
+                                    text = text.color(egui::Color32::from_rgb(255, 200, 100));
+                                    hover.push(tr_args("keybindings-reserved", &[("use", &use_)]));
+                                }
+                                if ui.button(text).on_hover_text(hover.join("\n")).clicked() {
+                                    remove = Some((action, shortcut));
+                                }
+                            }
+                            if self.capturing == Some(action) {
+                                ui.label(
+                                    egui::RichText::new(tr("keybindings-press-key"))
+                                        .italics()
+                                        .color(egui::Color32::from_rgb(100, 150, 255)),
+                                );
+                            } else if ui
+                                .small_button(tr("keybindings-add"))
+                                .on_hover_text(tr("keybindings-add-tooltip"))
+                                .clicked()
+                            {
+                                self.capturing = Some(action);
+                            }
+                        });
+                        if ui
+                            .add_enabled(
+                                !self.bindings.is_default(action),
+                                egui::Button::new(tr("keybindings-reset")),
+                            )
+                            .on_hover_text(tr("keybindings-reset-tooltip"))
+                            .clicked()
+                        {
+                            reset = Some(action);
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            if let Some((action, shortcut)) = remove {
+                self.bindings.remove(action, shortcut);
+                self.save();
+            }
+            if let Some(action) = reset {
+                self.bindings.reset(action);
+                self.save();
+            }
+
+            ui.add_space(10.0);
+            if ui.button(tr("keybindings-reset-all")).clicked() {
+                self.capturing = None;
+                self.bindings.reset_all();
+                self.save();
+            }
+
+            if let Some(msg) = &self.message {
+                let color = if msg.starts_with('✓') {
+                    egui::Color32::from_rgb(100, 200, 100)
+                } else {
+                    egui::Color32::from_rgb(220, 80, 80)
+                };
+                ui.colored_label(color, msg);
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(tr("settings-location"));
+                ui.code(
+                    keybindings::default_path()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_else(|| tr("keybindings-none")),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("settings-env-variable"));
+                ui.code(CONFIG_DIR_ENV);
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starts_without_capture() {
+        let panel = KeyBindingsPanel::with_bindings(KeyBindings::new(), None);
+        assert!(!panel.is_capturing());
+        assert_eq!(panel.bindings(), &KeyBindings::new());
+    }
+
+    #[test]
+    fn test_registers_actions_with_shortcuts() {
+        let panel = KeyBindingsPanel::with_bindings(KeyBindings::new(), None);
+        let mut registry = CommandRegistry::new();
+        panel.register_commands(&mut registry);
+        let compile = registry
+            .get(CommandKind::Action(Action::CompileShader))
+            .unwrap();
+        assert_eq!(compile.shortcut.as_deref(), Some("F5"));
+        assert!(registry
+            .get(CommandKind::Action(Action::CommandPalette))
+            .is_none());
+    }
+}
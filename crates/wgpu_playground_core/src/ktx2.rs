@@ -0,0 +1,216 @@
+//! Reading uncompressed KTX2 textures
+//!
+//! KTX2 is the Khronos container for GPU textures. The playground only needs
+//! the base level of plain 8-bit color textures to show them in the Textures
+//! tab, so [`decode`] reads that and converts it to RGBA; block-compressed and
+//! supercompressed files are reported as unsupported rather than parsed.
+
+use std::fmt;
+
+use crate::asset_loader::DecodedImage;
+
+/// First bytes of every KTX2 file
+pub const IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// Size of the header and the fixed part of the index before the level index
+const LEVEL_INDEX_OFFSET: usize = 80;
+
+/// Why a KTX2 file can't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ktx2Error {
+    /// The data doesn't start with the KTX2 identifier
+    NotKtx2,
+    /// The data ends before the header or the image it describes
+    Truncated,
+    /// A valid file this reader can't convert
+    Unsupported(String),
+}
+
+impl fmt::Display for Ktx2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ktx2Error::NotKtx2 => write!(f, "not a KTX2 file"),
+            Ktx2Error::Truncated => write!(f, "KTX2 file is truncated"),
+            Ktx2Error::Unsupported(what) => write!(f, "unsupported KTX2 file: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for Ktx2Error {}
+
+/// Whether `bytes` start like a KTX2 file
+pub fn is_ktx2(bytes: &[u8]) -> bool {
+    bytes.starts_with(&IDENTIFIER)
+}
+
+/// Channel layout of the Vulkan formats that can be converted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    R,
+    Rg,
+    Rgb,
+    Rgba,
+    Bgra,
+}
+
+impl Layout {
+    /// Layout of a `VkFormat`; sRGB and linear variants read alike
+    fn from_vk_format(format: u32) -> Option<Self> {
+        match format {
+            // VK_FORMAT_R8_UNORM, VK_FORMAT_R8_SRGB
+            9 | 15 => Some(Layout::R),
+            // VK_FORMAT_R8G8_UNORM, VK_FORMAT_R8G8_SRGB
+            16 | 22 => Some(Layout::Rg),
+            // VK_FORMAT_R8G8B8_UNORM, VK_FORMAT_R8G8B8_SRGB
+            23 | 29 => Some(Layout::Rgb),
+            // VK_FORMAT_R8G8B8A8_UNORM, VK_FORMAT_R8G8B8A8_SRGB
+            37 | 43 => Some(Layout::Rgba),
+            // VK_FORMAT_B8G8R8A8_UNORM, VK_FORMAT_B8G8R8A8_SRGB
+            44 | 50 => Some(Layout::Bgra),
+            _ => None,
+        }
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        match self {
+            Layout::R => 1,
+            Layout::Rg => 2,
+            Layout::Rgb => 3,
+            Layout::Rgba | Layout::Bgra => 4,
+        }
+    }
+
+    /// One pixel as RGBA; missing channels are zero and alpha opaque
+    fn to_rgba(self, pixel: &[u8]) -> [u8; 4] {
+        match self {
+            Layout::R => [pixel[0], 0, 0, 255],
+            Layout::Rg => [pixel[0], pixel[1], 0, 255],
+            Layout::Rgb => [pixel[0], pixel[1], pixel[2], 255],
+            Layout::Rgba => [pixel[0], pixel[1], pixel[2], pixel[3]],
+            Layout::Bgra => [pixel[2], pixel[1], pixel[0], pixel[3]],
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, Ktx2Error> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or(Ktx2Error::Truncated)
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, Ktx2Error> {
+    let low = read_u32(bytes, offset)? as u64;
+    let high = read_u32(bytes, offset + 4)? as u64;
+    Ok(high << 32 | low)
+}
+
+/// Decode the base level of a KTX2 file to RGBA
+///
+/// For array textures and cube maps the first layer or face is returned.
+pub fn decode(bytes: &[u8]) -> Result<DecodedImage, Ktx2Error> {
+    if !is_ktx2(bytes) {
+        return Err(Ktx2Error::NotKtx2);
+    }
+    let vk_format = read_u32(bytes, 12)?;
+    let width = read_u32(bytes, 20)?;
+    let height = read_u32(bytes, 24)?.max(1);
+    let depth = read_u32(bytes, 28)?;
+    let supercompression = read_u32(bytes, 44)?;
+
+    if vk_format == 0 {
+        return Err(Ktx2Error::Unsupported(
+            "Basis Universal textures need transcoding".to_string(),
+        ));
+    }
+    let layout = Layout::from_vk_format(vk_format)
+        .ok_or_else(|| Ktx2Error::Unsupported(format!("VkFormat {}", vk_format)))?;
+    if supercompression != 0 {
+        return Err(Ktx2Error::Unsupported(format!(
+            "supercompression scheme {}",
+            supercompression
+        )));
+    }
+    if depth > 1 {
+        return Err(Ktx2Error::Unsupported("3D textures".to_string()));
+    }
+    if width == 0 {
+        return Err(Ktx2Error::Unsupported("zero width".to_string()));
+    }
+
+    // Level 0, the largest, comes first in the level index
+    let offset = read_u64(bytes, LEVEL_INDEX_OFFSET)? as usize;
+    let pixel_count = width as usize * height as usize;
+    let size = pixel_count * layout.bytes_per_pixel();
+    let data = offset
+        .checked_add(size)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or(Ktx2Error::Truncated)?;
+
+    let rgba = data
+        .chunks_exact(layout.bytes_per_pixel())
+        .flat_map(|pixel| layout.to_rgba(pixel))
+        .collect();
+    Ok(DecodedImage {
+        width,
+        height,
+        rgba,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A minimal KTX2 file holding one level of `data`
+    fn ktx2(vk_format: u32, width: u32, height: u32, data: &[u8]) -> Vec<u8> {
+        let mut bytes = IDENTIFIER.to_vec();
+        // vkFormat, typeSize, width, height, depth, layers, faces, levels,
+        // supercompression
+        for value in [vk_format, 1, width, height, 0, 0, 1, 1, 0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        // DFD and key/value offsets and lengths, then the supercompression
+        // global data
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        let offset = (LEVEL_INDEX_OFFSET + 24) as u64;
+        for value in [offset, data.len() as u64, data.len() as u64] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn test_decode_rgba_and_bgra() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let rgba = decode(&ktx2(37, 2, 1, &data)).unwrap();
+        assert_eq!((rgba.width, rgba.height), (2, 1));
+        assert_eq!(rgba.rgba, data);
+
+        let bgra = decode(&ktx2(44, 2, 1, &data)).unwrap();
+        assert_eq!(bgra.rgba, [3, 2, 1, 4, 7, 6, 5, 8]);
+    }
+
+    #[test]
+    fn test_decode_expands_single_channel() {
+        let image = decode(&ktx2(9, 1, 2, &[10, 20])).unwrap();
+        assert_eq!(image.rgba, [10, 0, 0, 255, 20, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode(b"PNG").unwrap_err(), Ktx2Error::NotKtx2);
+        let mut truncated = ktx2(37, 2, 2, &[0; 16]);
+        truncated.truncate(truncated.len() - 1);
+        assert_eq!(decode(&truncated).unwrap_err(), Ktx2Error::Truncated);
+        // BC7
+        assert!(matches!(
+            decode(&ktx2(145, 4, 4, &[0; 16])),
+            Err(Ktx2Error::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Interactive lessons checked against the live playground state
+//!
+//! A lesson is a JSON file in the `lessons` directory describing a task:
+//! the explanation shown next to the panels, the partial playground state
+//! the learner is asked to reach, and checks that read the live state to
+//! tell whether they got there. Lessons run in order, each optionally
+//! starting from a [project template](crate::project_template).
+//!
+//! Checks are [validation](crate::validation) checks, so a lesson can assert
+//! anything an exercise can, including on the rendered output.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::project_template::ProjectTemplate;
+use crate::state::PlaygroundState;
+use crate::tutorial::HighlightTarget;
+use crate::validation::{Check, ExerciseValidator, Outcome};
+
+/// Serialized lessons, in the order they are taught
+const LESSONS: [&str; 5] = [
+    include_str!("../lessons/primitive_state.json"),
+    include_str!("../lessons/binding_a_texture.json"),
+    include_str!("../lessons/alpha_blending.json"),
+    include_str!("../lessons/depth_testing.json"),
+    include_str!("../lessons/compute_workgroups.json"),
+];
+
+/// One lesson of the tutorial mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lesson {
+    pub id: String,
+    pub title: String,
+    /// One line shown in the lesson list
+    pub summary: String,
+    /// Paragraphs explaining the concept and the task
+    pub explanation: Vec<String>,
+    /// Template loaded when the lesson starts, by its id
+    #[serde(default)]
+    pub start_template: Option<String>,
+    /// Panel the task is done in
+    #[serde(default)]
+    pub panel: Option<HighlightTarget>,
+    /// Partial playground state that completes the task, applied by "Show
+    /// Solution"
+    pub target: Value,
+    /// Checks that all pass once the task is done
+    pub checks: Vec<Check>,
+}
+
+/// Lessons completed and the one in progress, saved with the tutorial state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LessonProgress {
+    /// Id of the lesson shown in the side panel
+    pub current: Option<String>,
+    /// Ids of the lessons whose checks all passed
+    pub completed: Vec<String>,
+}
+
+impl LessonProgress {
+    pub fn is_completed(&self, lesson_id: &str) -> bool {
+        self.completed.iter().any(|id| id == lesson_id)
+    }
+
+    pub fn mark_completed(&mut self, lesson_id: &str) {
+        if !self.is_completed(lesson_id) {
+            self.completed.push(lesson_id.to_string());
+        }
+    }
+}
+
+/// Every lesson, in the order they are taught
+pub fn all_lessons() -> Vec<Lesson> {
+    LESSONS
+        .iter()
+        .map(|json| serde_json::from_str(json).expect("lessons shipped with the crate are valid"))
+        .collect()
+}
+
+/// Copy the fields of `patch` over `value`, recursing into objects and
+/// replacing everything else, arrays included
+fn merge(value: &mut Value, patch: &Value) {
+    match (value, patch) {
+        (Value::Object(map), Value::Object(patch)) => {
+            for (key, field) in patch {
+                match map.get_mut(key) {
+                    Some(existing) if !existing.is_null() => merge(existing, field),
+                    _ => {
+                        map.insert(key.clone(), field.clone());
+                    }
+                }
+            }
+        }
+        (value, patch) => *value = patch.clone(),
+    }
+}
+
+impl Lesson {
+    /// Template the lesson starts from
+    pub fn start_template(&self) -> Option<ProjectTemplate> {
+        let id = self.start_template.as_deref()?;
+        let template = ProjectTemplate::from_id(id);
+        if template.is_none() {
+            log::warn!("Lesson {} starts from unknown template {}", self.id, id);
+        }
+        template
+    }
+
+    /// Outcomes of the checks against a state, without a rendered output
+    pub fn evaluate(&self, state: &PlaygroundState) -> Vec<Outcome> {
+        ExerciseValidator::new().evaluate(&self.checks, state)
+    }
+
+    /// `state` with the lesson's target applied over it
+    pub fn solution(&self, state: &PlaygroundState) -> Result<PlaygroundState, serde_json::Error> {
+        let mut value = serde_json::to_value(state)?;
+        merge(&mut value, &self.target);
+        serde_json::from_value(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_replaces_arrays_and_keeps_other_fields() {
+        let mut value = serde_json::json!({
+            "panel": { "entries": [1, 2, 3], "label": "group" },
+            "other": null
+        });
+        merge(
+            &mut value,
+            &serde_json::json!({ "panel": { "entries": [4] }, "other": { "a": 1 } }),
+        );
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "panel": { "entries": [4], "label": "group" },
+                "other": { "a": 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_progress_completion_is_deduplicated() {
+        let mut progress = LessonProgress::default();
+        progress.mark_completed("depth_testing");
+        progress.mark_completed("depth_testing");
+        assert!(progress.is_completed("depth_testing"));
+        assert_eq!(progress.completed.len(), 1);
+    }
+}
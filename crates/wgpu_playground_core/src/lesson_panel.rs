@@ -0,0 +1,269 @@
+//! UI for interactive lessons: the lesson list and the side panel that
+//! checks the active lesson against the live state
+
+use crate::lesson::{all_lessons, Lesson, LessonProgress};
+use crate::state::PlaygroundState;
+use crate::tutorial::HighlightTarget;
+use crate::validation::{ExerciseValidator, Outcome};
+use egui::{Color32, RichText, Ui};
+use image::RgbaImage;
+
+/// Change to the playground requested by the lesson panel
+#[derive(Debug, Clone)]
+pub enum LessonAction {
+    /// Load a state into the active workspace
+    LoadState(Box<PlaygroundState>),
+    /// Switch to the panel a lesson's task is done in
+    OpenPanel(HighlightTarget),
+}
+
+pub struct LessonPanel {
+    lessons: Vec<Lesson>,
+    progress: LessonProgress,
+    validator: ExerciseValidator,
+}
+
+impl Default for LessonPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LessonPanel {
+    pub fn new() -> Self {
+        Self {
+            lessons: all_lessons(),
+            progress: LessonProgress::default(),
+            validator: ExerciseValidator::new(),
+        }
+    }
+
+    pub fn lessons(&self) -> &[Lesson] {
+        &self.lessons
+    }
+
+    pub fn progress(&self) -> &LessonProgress {
+        &self.progress
+    }
+
+    pub fn set_progress(&mut self, progress: LessonProgress) {
+        self.progress = progress;
+    }
+
+    /// Use a captured frame for the checks on the rendered output
+    pub fn set_rendered_output(&mut self, image: RgbaImage) {
+        self.validator.set_rendered(image);
+    }
+
+    /// Index of the lesson in progress
+    fn current_index(&self) -> Option<usize> {
+        let id = self.progress.current.as_deref()?;
+        self.lessons.iter().position(|lesson| lesson.id == id)
+    }
+
+    /// Lesson in progress, shown in the side panel
+    pub fn current(&self) -> Option<&Lesson> {
+        self.current_index().map(|index| &self.lessons[index])
+    }
+
+    /// Make a lesson current, returning the action loading its start state
+    pub fn start(&mut self, index: usize) -> Option<LessonAction> {
+        let lesson = self.lessons.get(index)?;
+        self.progress.current = Some(lesson.id.clone());
+        lesson
+            .start_template()
+            .map(|template| LessonAction::LoadState(Box::new(template.state())))
+    }
+
+    /// Stop showing the lesson in progress
+    pub fn exit(&mut self) {
+        self.progress.current = None;
+    }
+
+    /// List of lessons with their completion and buttons to start them
+    pub fn catalog_ui(&mut self, ui: &mut Ui) -> Option<LessonAction> {
+        let mut action = None;
+
+        ui.heading("📘 Lessons");
+        ui.label(format!(
+            "Short tasks checked live against the playground. {} of {} completed.",
+            self.lessons
+                .iter()
+                .filter(|lesson| self.progress.is_completed(&lesson.id))
+                .count(),
+            self.lessons.len()
+        ));
+        ui.add_space(5.0);
+
+        let current = self.current_index();
+        let mut start = None;
+        for (index, lesson) in self.lessons.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if self.progress.is_completed(&lesson.id) {
+                    ui.label(RichText::new("✓").color(Color32::GREEN));
+                } else {
+                    ui.label(RichText::new("○").color(Color32::GRAY));
+                }
+                ui.strong(format!("{}. {}", index + 1, lesson.title));
+                ui.weak(&lesson.summary);
+
+                if current == Some(index) {
+                    ui.label(RichText::new("In progress").color(Color32::LIGHT_BLUE));
+                    return;
+                }
+                let label = if self.progress.is_completed(&lesson.id) {
+                    "🔄 Restart"
+                } else {
+                    "▶ Start"
+                };
+                let mut button = ui.button(label);
+                if let Some(template) = lesson.start_template() {
+                    button = button.on_hover_text(format!(
+                        "Loads the {} template into the active workspace (undo restores your work)",
+                        template.name()
+                    ));
+                }
+                if button.clicked() {
+                    start = Some(index);
+                }
+            });
+        }
+        if let Some(index) = start {
+            action = self.start(index);
+        }
+
+        action
+    }
+
+    /// Side panel for the lesson in progress, checked against `live`
+    pub fn side_panel_ui(&mut self, ui: &mut Ui, live: &PlaygroundState) -> Option<LessonAction> {
+        let index = self.current_index()?;
+        let lesson = &self.lessons[index];
+        let outcomes = self.validator.evaluate(&lesson.checks, live);
+        let passed = outcomes
+            .iter()
+            .filter(|outcome| outcome.is_passed())
+            .count();
+        let done = passed == outcomes.len();
+        if done {
+            self.progress.mark_completed(&lesson.id);
+        }
+
+        let mut action = None;
+        let mut next = None;
+        let mut exit = false;
+
+        ui.weak(format!("Lesson {} of {}", index + 1, self.lessons.len()));
+        ui.heading(&lesson.title);
+        ui.add_space(5.0);
+        for paragraph in &lesson.explanation {
+            ui.label(paragraph);
+            ui.add_space(4.0);
+        }
+        ui.separator();
+
+        ui.strong("Checklist");
+        ui.add(
+            egui::ProgressBar::new(passed as f32 / outcomes.len().max(1) as f32).text(format!(
+                "{} / {} checks passing",
+                passed,
+                outcomes.len()
+            )),
+        );
+        for (check, outcome) in lesson.checks.iter().zip(&outcomes) {
+            ui.horizontal_top(|ui| {
+                match outcome {
+                    Outcome::Passed => ui.label(RichText::new("✓").color(Color32::GREEN)),
+                    Outcome::Failed(detail) => ui
+                        .label(RichText::new("✗").color(Color32::from_rgb(255, 100, 100)))
+                        .on_hover_text(detail),
+                    Outcome::Pending(_) => ui.label(RichText::new("⏳").color(Color32::GRAY)),
+                };
+                ui.vertical(|ui| {
+                    ui.label(&check.description);
+                    match outcome {
+                        Outcome::Passed => {}
+                        Outcome::Failed(_) => {
+                            if let Some(hint) = &check.hint {
+                                ui.weak(format!("💡 {}", hint));
+                            }
+                        }
+                        Outcome::Pending(reason) => {
+                            ui.weak(format!("{} (press F12 to capture one)", reason));
+                        }
+                    }
+                });
+            });
+        }
+        ui.add_space(5.0);
+
+        if done {
+            ui.label(
+                RichText::new("✓ Lesson complete!")
+                    .color(Color32::GREEN)
+                    .strong(),
+            );
+        }
+        ui.horizontal_wrapped(|ui| {
+            if let Some(panel) = lesson.panel {
+                if ui.button("📍 Go to Panel").clicked() {
+                    action = Some(LessonAction::OpenPanel(panel));
+                }
+            }
+            if !done
+                && ui
+                    .button("💡 Show Solution")
+                    .on_hover_text("Applies the lesson's target settings (undo restores your work)")
+                    .clicked()
+            {
+                match lesson.solution(live) {
+                    Ok(state) => action = Some(LessonAction::LoadState(Box::new(state))),
+                    Err(e) => log::error!("Invalid solution for lesson {}: {}", lesson.id, e),
+                }
+            }
+            if done && index + 1 < self.lessons.len() && ui.button("Next Lesson ▶").clicked() {
+                next = Some(index + 1);
+            }
+            if ui.button("✕ Exit Lesson").clicked() {
+                exit = true;
+            }
+        });
+
+        if let Some(index) = next {
+            action = self.start(index);
+        }
+        if exit {
+            self.exit();
+        }
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_loads_the_template_and_exit_clears_it() {
+        let mut panel = LessonPanel::new();
+        assert!(panel.current().is_none());
+
+        let action = panel.start(0);
+        assert!(matches!(action, Some(LessonAction::LoadState(_))));
+        assert_eq!(panel.current().unwrap().id, panel.lessons()[0].id);
+
+        panel.exit();
+        assert!(panel.current().is_none());
+        assert!(panel.start(panel.lessons().len()).is_none());
+    }
+
+    #[test]
+    fn test_unknown_current_lesson_is_ignored() {
+        let mut panel = LessonPanel::new();
+        panel.set_progress(LessonProgress {
+            current: Some("removed_lesson".to_string()),
+            completed: Vec::new(),
+        });
+        assert!(panel.current().is_none());
+    }
+}
@@ -3,66 +3,168 @@ pub mod adapter_selection;
 pub mod api_coverage;
 pub mod api_coverage_panel;
 pub mod api_reference_panel;
+pub mod asset_cache;
+pub mod asset_loader;
+pub mod asset_watcher;
 pub mod assets;
+pub mod async_compute;
+pub mod async_compute_panel;
+pub mod audio_input;
 pub mod bind_group;
 pub mod bind_group_layout_panel;
 pub mod bind_group_panel;
 pub mod bind_group_viz;
+pub mod bindless;
+pub mod bitonic_sort;
+pub mod blend_visualizer;
+pub mod boids;
 pub mod buffer;
+pub mod buffer_image;
 pub mod buffer_inspector;
+pub mod buffer_layout_check;
+pub mod buffer_io;
 pub mod buffer_panel;
 pub mod buffer_preview;
+pub mod camera;
+pub mod capability_snapshot;
+pub mod clipboard_image;
 pub mod code_generator;
 pub mod command_encoder;
+pub mod command_palette;
 pub mod command_recording_panel;
 pub mod compute;
 pub mod compute_dispatch_panel;
 pub mod compute_pass_encoder;
 pub mod compute_pipeline_panel;
 pub mod console;
+pub mod controller_input;
+pub mod cpu_profiler;
+pub mod cpu_profiler_panel;
 pub mod dawn_wrapper;
+pub mod debug_counters;
+pub mod debug_counters_panel;
 pub mod device_config;
 pub mod device_info;
+pub mod device_pipeline_cache;
+pub mod device_recovery;
 pub mod draw_command_panel;
+pub mod drop_router;
+pub mod embed;
 pub mod error;
 pub mod example_metadata;
 pub mod examples;
+pub mod exercise_panel;
+pub mod external_comparison_panel;
+pub mod file_watcher;
+pub mod fluid;
+pub mod fragment_playground;
+pub mod frame_capture;
+pub mod frame_pacing;
+pub mod frame_pacing_panel;
+pub mod geometry;
+pub mod grass;
+pub mod hardware_ray_tracing;
+pub mod headless_session;
+pub mod history;
+pub mod history_panel;
+pub mod i18n;
+pub mod image_processing;
 pub mod implementation;
+pub mod instanced_cubes;
+pub mod job_system;
+pub mod keybindings;
+pub mod keybindings_panel;
+pub mod ktx2;
 pub mod learning_path;
 pub mod learning_path_panel;
+pub mod lesson;
+pub mod lesson_panel;
+pub mod limits_explorer;
+pub mod lod;
+pub mod material;
 pub mod math_utils;
+pub mod memory_budget;
+pub mod mesh_import;
+pub mod mesh_shading;
+pub mod mip_debug;
 pub mod model_loader;
 pub mod model_loader_panel;
+pub mod operation_errors;
+pub mod parallel_algorithms;
 pub mod performance_metrics;
 pub mod performance_panel;
+pub mod permalink;
 pub mod pipeline_debugger;
+pub mod pipeline_diff;
 pub mod pipeline_layout;
 pub mod pipeline_preview;
+pub mod pixel_picker;
 pub mod preset;
 pub mod preset_panel;
+pub mod procedural_texture;
+pub mod project_template;
+pub mod puffin_viewer_panel;
 pub mod query_set;
 pub mod queue;
+pub mod ray_tracer;
 pub mod render_bundle_encoder;
+pub mod render_graph_analysis;
 pub mod render_pass_encoder;
 pub mod render_pass_panel;
 pub mod render_pipeline;
 pub mod render_pipeline_panel;
+pub mod render_targets;
+pub mod render_targets_panel;
+pub mod renderdoc_capture;
 pub mod rendering;
 pub mod report_html;
 pub mod resource_inspector;
 pub mod sampler;
 pub mod sampler_panel;
+pub mod scene;
+pub mod sdf_gallery;
 pub mod settings_panel;
 pub mod shader;
+pub mod shader_benchmark;
+pub mod shader_composer;
 pub mod shader_editor;
+pub mod shader_format;
+pub mod shader_history;
+pub mod shader_include;
+pub mod shader_lint;
+pub mod shader_preview;
+pub mod shader_translation;
+pub mod shader_translation_panel;
+pub mod shader_variants;
+pub mod shader_variants_panel;
 pub mod shader_watcher;
+pub mod shadertoy_shim;
+pub mod shadow_bias;
+pub mod source_viewer;
 pub mod state;
+pub mod stencil_demo;
+pub mod stencil_demo_panel;
 pub mod surface;
+pub mod surface_config;
+pub mod surface_config_panel;
+pub mod svg;
+pub mod tangent_debug;
+pub mod terrain;
+pub mod text;
 pub mod texture;
 pub mod texture_inspector;
 pub mod texture_panel;
 pub mod texture_preview;
+pub mod theme;
+pub mod tile_streaming;
 pub mod tooltip;
 pub mod tutorial;
 pub mod tutorial_panel;
+pub mod uniform_tweaker;
+pub mod upload_belt;
+pub mod validation;
 pub mod visual_regression;
+pub mod volumetric_fog;
+pub mod water;
+pub mod workgroup_tuner;
+pub mod workgroup_tuner_panel;
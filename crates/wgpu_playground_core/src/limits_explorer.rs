@@ -0,0 +1,602 @@
+//! Interactive probes for device limits
+//!
+//! The limits a device reports are what wgpu validates against, but
+//! drivers and backends occasionally disagree with them. Each
+//! [`LimitProbe`] creates the resources a limit governs at exactly the
+//! reported value, where creation must succeed, and optionally one step
+//! past it, where the device must reject it. Probes run inside error
+//! scopes, so failures are reported as results instead of reaching the
+//! uncaptured error handler.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A limit that can be probed by creating resources
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitProbe {
+    TextureDimension1d,
+    TextureDimension2d,
+    TextureDimension3d,
+    TextureArrayLayers,
+    BindGroups,
+    BindingsPerBindGroup,
+    BufferSize,
+    UniformBufferBindingSize,
+    StorageBufferBindingSize,
+    ComputeWorkgroupSizeX,
+}
+
+impl LimitProbe {
+    /// All probes, in display order
+    pub const ALL: [LimitProbe; 10] = [
+        LimitProbe::TextureDimension1d,
+        LimitProbe::TextureDimension2d,
+        LimitProbe::TextureDimension3d,
+        LimitProbe::TextureArrayLayers,
+        LimitProbe::BindGroups,
+        LimitProbe::BindingsPerBindGroup,
+        LimitProbe::BufferSize,
+        LimitProbe::UniformBufferBindingSize,
+        LimitProbe::StorageBufferBindingSize,
+        LimitProbe::ComputeWorkgroupSizeX,
+    ];
+
+    /// Name of the probed field of [`wgpu::Limits`]
+    pub fn limit_name(&self) -> &'static str {
+        match self {
+            LimitProbe::TextureDimension1d => "max_texture_dimension_1d",
+            LimitProbe::TextureDimension2d => "max_texture_dimension_2d",
+            LimitProbe::TextureDimension3d => "max_texture_dimension_3d",
+            LimitProbe::TextureArrayLayers => "max_texture_array_layers",
+            LimitProbe::BindGroups => "max_bind_groups",
+            LimitProbe::BindingsPerBindGroup => "max_bindings_per_bind_group",
+            LimitProbe::BufferSize => "max_buffer_size",
+            LimitProbe::UniformBufferBindingSize => "max_uniform_buffer_binding_size",
+            LimitProbe::StorageBufferBindingSize => "max_storage_buffer_binding_size",
+            LimitProbe::ComputeWorkgroupSizeX => "max_compute_workgroup_size_x",
+        }
+    }
+
+    /// What the probe creates
+    pub fn description(&self) -> &'static str {
+        match self {
+            LimitProbe::TextureDimension1d => "1D R8 texture that wide",
+            LimitProbe::TextureDimension2d => "2D R8 texture that wide and 1 texel high",
+            LimitProbe::TextureDimension3d => "3D R8 texture that wide, 1 texel high and deep",
+            LimitProbe::TextureArrayLayers => "1×1 R8 texture with that many layers",
+            LimitProbe::BindGroups => {
+                "Compute pipeline with that many bind groups, set and dispatched"
+            }
+            LimitProbe::BindingsPerBindGroup => "Bind group layout using the highest binding index",
+            LimitProbe::BufferSize => "Buffer of that many bytes",
+            LimitProbe::UniformBufferBindingSize => "Bind group with a uniform binding that large",
+            LimitProbe::StorageBufferBindingSize => "Bind group with a storage binding that large",
+            LimitProbe::ComputeWorkgroupSizeX => "Compute pipeline with @workgroup_size(N)",
+        }
+    }
+
+    /// Value of the limit in `limits`
+    pub fn reported(&self, limits: &wgpu::Limits) -> u64 {
+        match self {
+            LimitProbe::TextureDimension1d => limits.max_texture_dimension_1d.into(),
+            LimitProbe::TextureDimension2d => limits.max_texture_dimension_2d.into(),
+            LimitProbe::TextureDimension3d => limits.max_texture_dimension_3d.into(),
+            LimitProbe::TextureArrayLayers => limits.max_texture_array_layers.into(),
+            LimitProbe::BindGroups => limits.max_bind_groups.into(),
+            LimitProbe::BindingsPerBindGroup => limits.max_bindings_per_bind_group.into(),
+            LimitProbe::BufferSize => limits.max_buffer_size,
+            LimitProbe::UniformBufferBindingSize => limits.max_uniform_buffer_binding_size,
+            LimitProbe::StorageBufferBindingSize => limits.max_storage_buffer_binding_size,
+            LimitProbe::ComputeWorkgroupSizeX => limits.max_compute_workgroup_size_x.into(),
+        }
+    }
+
+    /// Smallest value past `limit` that the device must reject
+    ///
+    /// Buffer bindings step by 4 bytes, since storage binding sizes must
+    /// be a multiple of 4 regardless of the limit.
+    pub fn past_limit(&self, limit: u64) -> u64 {
+        match self {
+            LimitProbe::UniformBufferBindingSize | LimitProbe::StorageBufferBindingSize => {
+                limit.saturating_add(4)
+            }
+            _ => limit.saturating_add(1),
+        }
+    }
+
+    /// Bytes of GPU memory the probe allocates at `value`, if significant
+    pub fn allocation(&self, value: u64) -> Option<u64> {
+        match self {
+            LimitProbe::BufferSize
+            | LimitProbe::UniformBufferBindingSize
+            | LimitProbe::StorageBufferBindingSize => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Create the resources at `value`
+    ///
+    /// Device errors are reported through error scopes; an `Err` here
+    /// means the value could not even be expressed in the API.
+    fn attempt(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        value: u64,
+    ) -> Result<(), String> {
+        let value_u32 =
+            || u32::try_from(value).map_err(|_| format!("{} does not fit in 32 bits", value));
+        match self {
+            LimitProbe::TextureDimension1d => {
+                create_texture(device, wgpu::TextureDimension::D1, value_u32()?, 1);
+            }
+            LimitProbe::TextureDimension2d => {
+                create_texture(device, wgpu::TextureDimension::D2, value_u32()?, 1);
+            }
+            LimitProbe::TextureDimension3d => {
+                create_texture(device, wgpu::TextureDimension::D3, value_u32()?, 1);
+            }
+            LimitProbe::TextureArrayLayers => {
+                create_texture(device, wgpu::TextureDimension::D2, 1, value_u32()?);
+            }
+            LimitProbe::BindGroups => dispatch_with_bind_groups(device, queue, value_u32()?),
+            LimitProbe::BindingsPerBindGroup => {
+                // Binding indices must be below the limit
+                let binding = value_u32()?.saturating_sub(1);
+                let _layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("Limit Probe Bind Group Layout"),
+                    entries: &[wgpu::BindGroupLayoutEntry {
+                        binding,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    }],
+                });
+            }
+            LimitProbe::BufferSize => {
+                let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Limit Probe Buffer"),
+                    size: value,
+                    usage: wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                buffer.destroy();
+            }
+            LimitProbe::UniformBufferBindingSize => bind_buffer(
+                device,
+                value,
+                wgpu::BufferUsages::UNIFORM,
+                wgpu::BufferBindingType::Uniform,
+            ),
+            LimitProbe::StorageBufferBindingSize => bind_buffer(
+                device,
+                value,
+                wgpu::BufferUsages::STORAGE,
+                wgpu::BufferBindingType::Storage { read_only: true },
+            ),
+            LimitProbe::ComputeWorkgroupSizeX => {
+                let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("Limit Probe Shader"),
+                    source: wgpu::ShaderSource::Wgsl(
+                        format!("@compute @workgroup_size({}) fn main() {{}}", value_u32()?).into(),
+                    ),
+                });
+                let _pipeline = create_compute_pipeline(device, &shader, None);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn create_texture(
+    device: &wgpu::Device,
+    dimension: wgpu::TextureDimension,
+    width: u32,
+    layers: u32,
+) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Limit Probe Texture"),
+        size: wgpu::Extent3d {
+            width,
+            height: 1,
+            depth_or_array_layers: layers,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    texture.destroy();
+}
+
+fn bind_buffer(
+    device: &wgpu::Device,
+    size: u64,
+    usage: wgpu::BufferUsages,
+    ty: wgpu::BufferBindingType,
+) {
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Limit Probe Binding Buffer"),
+        size,
+        usage,
+        mapped_at_creation: false,
+    });
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Limit Probe Bind Group Layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let _bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Limit Probe Bind Group"),
+        layout: &layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    });
+    buffer.destroy();
+}
+
+fn create_compute_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: Option<&wgpu::PipelineLayout>,
+) -> wgpu::ComputePipeline {
+    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("Limit Probe Pipeline"),
+        layout,
+        module: shader,
+        entry_point: Some("main"),
+        compilation_options: Default::default(),
+        cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+    })
+}
+
+/// Dispatch a pipeline with `count` empty bind groups, all of them set
+fn dispatch_with_bind_groups(device: &wgpu::Device, queue: &wgpu::Queue, count: u32) {
+    let layouts: Vec<wgpu::BindGroupLayout> = (0..count)
+        .map(|_| {
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Limit Probe Empty Layout"),
+                entries: &[],
+            })
+        })
+        .collect();
+    let layout_refs: Vec<Option<&wgpu::BindGroupLayout>> = layouts.iter().map(Some).collect();
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Limit Probe Pipeline Layout"),
+        bind_group_layouts: &layout_refs,
+        immediate_size: 0,
+    });
+    let bind_groups: Vec<wgpu::BindGroup> = layouts
+        .iter()
+        .map(|layout| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Limit Probe Empty Bind Group"),
+                layout,
+                entries: &[],
+            })
+        })
+        .collect();
+
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Limit Probe Shader"),
+        source: wgpu::ShaderSource::Wgsl("@compute @workgroup_size(1) fn main() {}".into()),
+    });
+    let pipeline = create_compute_pipeline(device, &shader, Some(&pipeline_layout));
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Limit Probe Encoder"),
+    });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Limit Probe Pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&pipeline);
+        for (index, bind_group) in bind_groups.iter().enumerate() {
+            pass.set_bind_group(index as u32, bind_group, &[]);
+        }
+        pass.dispatch_workgroups(1, 1, 1);
+    }
+    queue.submit(Some(encoder.finish()));
+}
+
+/// Outcome of running a probe at one value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeResult {
+    pub probe: LimitProbe,
+    pub value: u64,
+    /// Whether the device should accept the value
+    pub expect_success: bool,
+    /// First error the device reported, if any
+    pub error: Option<String>,
+}
+
+impl ProbeResult {
+    /// Whether the device behaved as its reported limit says
+    pub fn passed(&self) -> bool {
+        self.error.is_none() == self.expect_success
+    }
+
+    /// One-line description of the outcome
+    pub fn summary(&self) -> String {
+        let outcome = match (&self.error, self.expect_success) {
+            (None, true) => "accepted".to_string(),
+            (None, false) => "accepted, although past the reported limit".to_string(),
+            (Some(_), false) => "rejected as expected".to_string(),
+            (Some(error), true) => format!("rejected within the reported limit: {}", error),
+        };
+        format!(
+            "{} {} = {}: {}",
+            if self.passed() { "✅" } else { "❌" },
+            self.probe.limit_name(),
+            self.value,
+            outcome
+        )
+    }
+}
+
+/// Run `probe` at `value` inside error scopes and report the result
+///
+/// On native platforms `on_done` is called before this returns. On the
+/// web, errors arrive asynchronously and `on_done` runs later.
+pub fn run_probe(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    probe: LimitProbe,
+    value: u64,
+    expect_success: bool,
+    on_done: impl FnOnce(ProbeResult) + 'static,
+) {
+    log::info!("Probing {} = {}", probe.limit_name(), value);
+    let validation = device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let out_of_memory = device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+    let internal = device.push_error_scope(wgpu::ErrorFilter::Internal);
+    let attempt = probe.attempt(device, queue, value);
+    // Scopes must be popped innermost first
+    let pops = [internal.pop(), out_of_memory.pop(), validation.pop()];
+
+    let finish = async move {
+        let mut error = attempt.err();
+        for pop in pops {
+            if let (Some(e), None) = (pop.await, &error) {
+                error = Some(crate::error::Error::from(e).message);
+            }
+        }
+        on_done(ProbeResult {
+            probe,
+            value,
+            expect_success,
+            error,
+        });
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    pollster::block_on(finish);
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(finish);
+}
+
+/// Limits explorer section of the device info tab
+///
+/// Cloning is cheap and clones share the results.
+#[derive(Debug, Clone)]
+pub struct LimitsExplorer {
+    results: Arc<Mutex<Vec<ProbeResult>>>,
+    /// Also check that one step past each limit is rejected
+    pub probe_past_limit: bool,
+}
+
+impl Default for LimitsExplorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LimitsExplorer {
+    pub fn new() -> Self {
+        Self {
+            results: Arc::default(),
+            probe_past_limit: true,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<ProbeResult>> {
+        self.results.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Results of all probes run so far, oldest first
+    pub fn results(&self) -> Vec<ProbeResult> {
+        self.lock().clone()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Probe the device's limit for `probe`, appending the results
+    pub fn run(&self, device: &wgpu::Device, queue: &wgpu::Queue, probe: LimitProbe) {
+        let limit = probe.reported(&device.limits());
+        let mut values = vec![(limit, true)];
+        if self.probe_past_limit {
+            values.push((probe.past_limit(limit), false));
+        }
+        for (value, expect_success) in values {
+            let explorer = self.clone();
+            run_probe(device, queue, probe, value, expect_success, move |result| {
+                if !result.passed() {
+                    log::warn!("Limit probe failed: {}", result.summary());
+                }
+                explorer.lock().push(result);
+            });
+        }
+    }
+
+    /// Run every probe
+    pub fn run_all(&self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        for probe in LimitProbe::ALL {
+            self.run(device, queue, probe);
+        }
+    }
+
+    /// Render the probe table and results
+    ///
+    /// `adapter_limits` are shown next to the device's for comparison;
+    /// probes always use the device's limits.
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: Option<(&wgpu::Device, &wgpu::Queue)>,
+        adapter_limits: &wgpu::Limits,
+    ) {
+        ui.heading("🧪 Limits Explorer");
+        ui.separator();
+        ui.label(
+            "Create resources at the device's reported limits to check the driver really \
+            supports them. Probes use the device's limits; request higher limits in Device \
+            Config to probe up to the adapter's.",
+        );
+        ui.add_space(5.0);
+
+        let Some((device, queue)) = device else {
+            ui.label("ℹ️ No device available to probe.");
+            return;
+        };
+        let device_limits = device.limits();
+
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.probe_past_limit,
+                "Also check one step past the limit is rejected",
+            );
+            if ui.button("▶ Run All").clicked() {
+                self.run_all(device, queue);
+            }
+            if ui.button("🗑 Clear Results").clicked() {
+                self.clear();
+            }
+        });
+        ui.add_space(5.0);
+
+        let results = self.results();
+        egui::Grid::new("limits_explorer_probes")
+            .num_columns(5)
+            .spacing([14.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["Limit", "Device", "Adapter", "", "Status"] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for probe in LimitProbe::ALL {
+                    let limit = probe.reported(&device_limits);
+                    ui.monospace(probe.limit_name())
+                        .on_hover_text(probe.description());
+                    ui.label(limit.to_string());
+                    ui.label(probe.reported(adapter_limits).to_string());
+
+                    let mut button = ui.small_button("▶ Probe");
+                    if let Some(bytes) = probe.allocation(limit) {
+                        button = button.on_hover_text(format!(
+                            "Allocates {:.1} MiB of GPU memory",
+                            bytes as f64 / (1024.0 * 1024.0)
+                        ));
+                    }
+                    if button.clicked() {
+                        self.run(device, queue, probe);
+                    }
+
+                    let mut runs = results.iter().filter(|r| r.probe == probe).peekable();
+                    if runs.peek().is_none() {
+                        ui.label("—");
+                    } else if runs.all(ProbeResult::passed) {
+                        ui.colored_label(egui::Color32::from_rgb(100, 200, 100), "✅ Pass");
+                    } else {
+                        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), "❌ Fail");
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if results.is_empty() {
+            return;
+        }
+        ui.add_space(5.0);
+        let failed = results.iter().filter(|r| !r.passed()).count();
+        ui.label(format!("{} probe(s) run, {} failed", results.len(), failed));
+        egui::ScrollArea::vertical()
+            .id_salt("limits_explorer_results")
+            .max_height(150.0)
+            .show(ui, |ui| {
+                for result in &results {
+                    let color = if result.passed() {
+                        egui::Color32::from_rgb(100, 200, 100)
+                    } else {
+                        egui::Color32::from_rgb(255, 100, 100)
+                    };
+                    ui.colored_label(color, result.summary());
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reported_limits() {
+        let limits = wgpu::Limits::default();
+        assert_eq!(
+            LimitProbe::TextureDimension2d.reported(&limits),
+            u64::from(limits.max_texture_dimension_2d)
+        );
+        assert_eq!(
+            LimitProbe::BufferSize.reported(&limits),
+            limits.max_buffer_size
+        );
+        for probe in LimitProbe::ALL {
+            assert!(probe.reported(&limits) > 0, "{}", probe.limit_name());
+        }
+    }
+
+    #[test]
+    fn test_past_limit() {
+        assert_eq!(LimitProbe::BindGroups.past_limit(4), 5);
+        assert_eq!(LimitProbe::StorageBufferBindingSize.past_limit(1024), 1028);
+        assert_eq!(LimitProbe::BufferSize.past_limit(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_result_passed() {
+        let mut result = ProbeResult {
+            probe: LimitProbe::BindGroups,
+            value: 4,
+            expect_success: true,
+            error: None,
+        };
+        assert!(result.passed());
+        assert!(result.summary().starts_with("✅ max_bind_groups = 4"));
+
+        result.error = Some("Too many bind groups".to_string());
+        assert!(!result.passed());
+        assert!(result.summary().contains("Too many bind groups"));
+
+        result.expect_success = false;
+        assert!(result.passed());
+    }
+}
@@ -0,0 +1,1199 @@
+//! Level-of-detail mesh switching with a screen-space error metric
+//!
+//! A field of spheres is drawn with three icosphere meshes of decreasing
+//! detail. Every mesh knows its geometric error, the largest distance
+//! between its triangles and the true sphere. Projected to the screen, the
+//! error of an object shrinks with its distance; each object uses the
+//! coarsest mesh whose projected error stays below a tolerance in pixels.
+//!
+//! Objects outside the view frustum are culled, and the remaining ones are
+//! sorted into one list of object indices per mesh, each drawn instanced.
+//! The selection runs either on the CPU, which uploads the lists and
+//! issues `draw_indexed`, or in a compute shader that appends to the lists
+//! and counts the instances straight into `DrawIndexedIndirect` arguments,
+//! so the CPU never learns what is drawn. The GPU path's counts are read
+//! back a frame or two later, without stalling, for display.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{frustum_planes, normalize, sphere_in_frustum, Mat4};
+use crate::upload_belt;
+use wgpu::util::DeviceExt;
+
+/// Number of meshes, from the most detailed to the coarsest
+pub const LOD_COUNT: usize = 3;
+
+/// Icosphere subdivisions of every mesh
+pub const LOD_SUBDIVISIONS: [u32; LOD_COUNT] = [3, 2, 1];
+
+/// Largest number of objects; also the length of every visible list
+pub const MAX_OBJECTS: u32 = 4096;
+
+/// Objects along each side of the default grid
+const GRID_SIDE: u32 = 32;
+
+/// Distance between neighboring object centers
+const SPACING: f32 = 3.0;
+
+/// `forced_lod` value of the shader selecting by error
+const AUTO_LOD: u32 = u32::MAX;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Size of one visible list in bytes, a multiple of the 256 byte offset
+/// alignment so that the lists can be bound with dynamic offsets
+const LIST_SIZE: u64 = MAX_OBJECTS as u64 * 4;
+
+const DRAW_ARGS_SIZE: u64 = std::mem::size_of::<wgpu::util::DrawIndexedIndirectArgs>() as u64;
+
+pub const LOD_SELECT_SHADER: &str = r#"// LOD selection: one thread per object culls it against the view
+// frustum, picks the coarsest mesh whose screen-space error is small
+// enough and appends the object to that mesh's indirect draw.
+
+const LOD_COUNT: u32 = 3u;
+const MAX_OBJECTS: u32 = 4096u;
+const AUTO_LOD: u32 = 0xffffffffu;
+
+struct Object {
+    position: vec3<f32>,
+    radius: f32,
+    color: vec3<f32>,
+    _padding: f32,
+}
+
+struct Params {
+    planes: array<vec4<f32>, 6>,
+    eye: vec3<f32>,
+    // Pixels covered by one world unit at distance one
+    pixel_scale: f32,
+    // Geometric error of every mesh for a unit sphere
+    lod_errors: vec4<f32>,
+    // Largest screen-space error in pixels
+    tolerance: f32,
+    object_count: u32,
+    forced_lod: u32,
+    cull: u32,
+}
+
+// Laid out like wgpu::util::DrawIndexedIndirectArgs
+struct DrawArgs {
+    index_count: u32,
+    instance_count: atomic<u32>,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> objects: array<Object>;
+@group(0) @binding(2) var<storage, read_write> draws: array<DrawArgs, LOD_COUNT>;
+// LOD_COUNT lists of MAX_OBJECTS object indices, one per mesh
+@group(0) @binding(3) var<storage, read_write> visible: array<u32>;
+
+fn in_frustum(center: vec3<f32>, radius: f32) -> bool {
+    for (var i = 0u; i < 6u; i++) {
+        let plane = params.planes[i];
+        if dot(plane.xyz, center) + plane.w < -radius {
+            return false;
+        }
+    }
+    return true;
+}
+
+fn select_lod(object: Object) -> u32 {
+    if params.forced_lod != AUTO_LOD {
+        return params.forced_lod;
+    }
+    let distance = max(length(object.position - params.eye) - object.radius, 1e-3);
+    // Coarsest mesh whose error, projected to the screen, stays below the tolerance
+    for (var lod = LOD_COUNT - 1u; lod > 0u; lod--) {
+        let error = params.lod_errors[lod] * object.radius * params.pixel_scale / distance;
+        if error <= params.tolerance {
+            return lod;
+        }
+    }
+    return 0u;
+}
+
+@compute @workgroup_size(64)
+fn select_lods(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= params.object_count {
+        return;
+    }
+    let object = objects[index];
+    if params.cull != 0u && !in_frustum(object.position, object.radius) {
+        return;
+    }
+    let lod = select_lod(object);
+    let slot = atomicAdd(&draws[lod].instance_count, 1u);
+    visible[lod * MAX_OBJECTS + slot] = index;
+}
+"#;
+
+pub const LOD_RENDER_SHADER: &str = r#"// LOD Meshes Example
+// Every object is a sphere drawn with one of three meshes of decreasing
+// detail. Each mesh is drawn instanced from its list of object indices,
+// filled on the CPU or by the LOD selection compute shader.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    show_lod: u32,
+}
+
+struct Object {
+    position: vec3<f32>,
+    radius: f32,
+    color: vec3<f32>,
+    _padding: f32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var<storage, read> objects: array<Object>;
+// The list of the mesh being drawn, selected with a dynamic offset
+@group(0) @binding(2) var<storage, read> visible: array<u32>;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    // Which mesh the vertex belongs to
+    @location(1) lod: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+}
+
+fn lod_tint(lod: u32) -> vec3<f32> {
+    var tints = array<vec3<f32>, 3>(
+        vec3<f32>(0.3, 1.0, 0.3),
+        vec3<f32>(1.0, 0.9, 0.2),
+        vec3<f32>(1.0, 0.3, 0.3),
+    );
+    return tints[min(lod, 2u)];
+}
+
+@vertex
+fn vs_main(vertex: VertexInput, @builtin(instance_index) instance: u32) -> VertexOutput {
+    let object = objects[visible[instance]];
+    let world = object.position + vertex.position * object.radius;
+    // A unit sphere's position is its normal
+    let light = max(dot(vertex.position, normalize(vec3<f32>(0.4, 0.8, 0.5))), 0.0);
+    var color = object.color;
+    if uniforms.show_lod != 0u {
+        color = lod_tint(vertex.lod);
+    }
+
+    var out: VertexOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(world, 1.0);
+    out.color = color * (0.25 + 0.75 * light);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+"#;
+
+/// Where LODs are selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionPath {
+    /// The CPU culls and selects, uploads the lists and draws directly
+    Cpu,
+    /// A compute shader culls and selects, writing indirect draws
+    Gpu,
+}
+
+impl SelectionPath {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SelectionPath::Cpu => "CPU selection",
+            SelectionPath::Gpu => "GPU selection (indirect)",
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LodVertex {
+    pub position: [f32; 3],
+    /// Index of the mesh the vertex belongs to
+    pub lod: u32,
+}
+
+/// An object of the scene: a sphere, laid out like the shaders' `Object`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LodObject {
+    pub position: [f32; 3],
+    pub radius: f32,
+    pub color: [f32; 3],
+    pub _padding: f32,
+}
+
+/// A unit icosphere with the given number of subdivisions
+///
+/// Triangles are wound counter-clockwise seen from outside.
+pub fn icosphere(subdivisions: u32) -> (Vec<[f32; 3]>, Vec<u16>) {
+    let t = (1.0 + 5.0_f32.sqrt()) / 2.0;
+    let mut positions: Vec<[f32; 3]> = [
+        [-1.0, t, 0.0],
+        [1.0, t, 0.0],
+        [-1.0, -t, 0.0],
+        [1.0, -t, 0.0],
+        [0.0, -1.0, t],
+        [0.0, 1.0, t],
+        [0.0, -1.0, -t],
+        [0.0, 1.0, -t],
+        [t, 0.0, -1.0],
+        [t, 0.0, 1.0],
+        [-t, 0.0, -1.0],
+        [-t, 0.0, 1.0],
+    ]
+    .into_iter()
+    .map(normalize)
+    .collect();
+    let mut triangles: Vec<[u16; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints: HashMap<(u16, u16), u16> = HashMap::new();
+        let mut midpoint = |a: u16, b: u16| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let (pa, pb) = (positions[a as usize], positions[b as usize]);
+                positions.push(normalize(std::array::from_fn(|i| (pa[i] + pb[i]) / 2.0)));
+                (positions.len() - 1) as u16
+            })
+        };
+        triangles = triangles
+            .iter()
+            .flat_map(|&[a, b, c]| {
+                let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+    (positions, triangles.into_iter().flatten().collect())
+}
+
+/// Largest distance between a triangle of a unit sphere mesh and the
+/// sphere, reached at the triangle centers
+pub fn geometric_error(positions: &[[f32; 3]], indices: &[u16]) -> f32 {
+    indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            let center: [f32; 3] = std::array::from_fn(|i| {
+                triangle
+                    .iter()
+                    .map(|&v| positions[v as usize][i])
+                    .sum::<f32>()
+                    / 3.0
+            });
+            1.0 - (center[0] * center[0] + center[1] * center[1] + center[2] * center[2]).sqrt()
+        })
+        .fold(0.0, f32::max)
+}
+
+/// Where one mesh lives in the shared vertex and index buffers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodMesh {
+    pub first_index: u32,
+    pub index_count: u32,
+    pub base_vertex: i32,
+    /// Geometric error for a unit sphere, see [`geometric_error`]
+    pub error: f32,
+}
+
+impl LodMesh {
+    pub fn triangles(&self) -> u32 {
+        self.index_count / 3
+    }
+}
+
+/// The meshes of [`LOD_SUBDIVISIONS`] packed into one vertex and one index
+/// buffer
+pub fn lod_meshes() -> (Vec<LodVertex>, Vec<u16>, [LodMesh; LOD_COUNT]) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let meshes = std::array::from_fn(|lod| {
+        let (positions, mesh_indices) = icosphere(LOD_SUBDIVISIONS[lod]);
+        let mesh = LodMesh {
+            first_index: indices.len() as u32,
+            index_count: mesh_indices.len() as u32,
+            base_vertex: vertices.len() as i32,
+            error: geometric_error(&positions, &mesh_indices),
+        };
+        vertices.extend(positions.into_iter().map(|position| LodVertex {
+            position,
+            lod: lod as u32,
+        }));
+        indices.extend(mesh_indices);
+        mesh
+    });
+    (vertices, indices, meshes)
+}
+
+/// A square grid of `side` x `side` spheres of varying size and color on
+/// the XZ plane, centered on the origin
+pub fn object_grid(side: u32) -> Vec<LodObject> {
+    let half = (side as f32 - 1.0) / 2.0;
+    (0..side * side)
+        .map(|i| {
+            let (x, z) = ((i % side) as f32 - half, (i / side) as f32 - half);
+            // Cheap hash for a stable per-object variation
+            let hash = i.wrapping_mul(2_654_435_761) >> 8;
+            let variation = |shift: u32| ((hash >> shift) & 0xff) as f32 / 255.0;
+            LodObject {
+                position: [x * SPACING, 0.0, z * SPACING],
+                radius: 0.6 + 0.7 * variation(0),
+                color: [
+                    0.3 + 0.7 * variation(4),
+                    0.3 + 0.7 * variation(8),
+                    0.3 + 0.7 * variation(12),
+                ],
+                _padding: 0.0,
+            }
+        })
+        .collect()
+}
+
+/// What the camera sees, for culling and the screen-space error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodView {
+    pub view_proj: Mat4,
+    pub eye: [f32; 3],
+    /// Vertical field of view in radians
+    pub fov_y: f32,
+    /// Height of the target in pixels
+    pub viewport_height: u32,
+}
+
+impl LodView {
+    /// Pixels covered by one world unit at distance one
+    pub fn pixel_scale(&self) -> f32 {
+        self.viewport_height as f32 / (2.0 * (self.fov_y / 2.0).tan())
+    }
+}
+
+/// How LODs are selected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodSettings {
+    /// Largest screen-space error in pixels
+    pub tolerance: f32,
+    /// Draw every object with this mesh instead of selecting by error
+    pub forced_lod: Option<usize>,
+    /// Skip objects outside the view frustum
+    pub cull: bool,
+}
+
+impl Default for LodSettings {
+    fn default() -> Self {
+        Self {
+            tolerance: 1.0,
+            forced_lod: None,
+            cull: true,
+        }
+    }
+}
+
+/// Mesh of an object at `distance` from the eye: the coarsest whose error,
+/// projected to the screen, is at most `tolerance` pixels
+///
+/// `errors` are the geometric errors of the meshes for a unit sphere.
+pub fn select_lod(
+    errors: &[f32; LOD_COUNT],
+    radius: f32,
+    distance: f32,
+    pixel_scale: f32,
+    tolerance: f32,
+) -> usize {
+    let distance = distance.max(1e-3);
+    (1..LOD_COUNT)
+        .rev()
+        .find(|&lod| errors[lod] * radius * pixel_scale / distance <= tolerance)
+        .unwrap_or(0)
+}
+
+/// Objects drawn with every mesh, and the objects culled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LodCounts {
+    pub instances: [u32; LOD_COUNT],
+    pub culled: u32,
+}
+
+impl LodCounts {
+    /// Triangles drawn with every mesh
+    pub fn triangles(&self, meshes: &[LodMesh; LOD_COUNT]) -> [u64; LOD_COUNT] {
+        std::array::from_fn(|lod| {
+            u64::from(self.instances[lod]) * u64::from(meshes[lod].triangles())
+        })
+    }
+}
+
+/// Sort the objects into one list of indices per mesh, as the selection
+/// shader does
+pub fn select_lods(
+    objects: &[LodObject],
+    meshes: &[LodMesh; LOD_COUNT],
+    view: &LodView,
+    settings: &LodSettings,
+) -> [Vec<u32>; LOD_COUNT] {
+    let planes = frustum_planes(&view.view_proj);
+    let errors = meshes.map(|mesh| mesh.error);
+    let mut lists: [Vec<u32>; LOD_COUNT] = Default::default();
+    for (index, object) in objects.iter().enumerate() {
+        if settings.cull && !sphere_in_frustum(&planes, object.position, object.radius) {
+            continue;
+        }
+        let lod = settings.forced_lod.unwrap_or_else(|| {
+            let offset: [f32; 3] = std::array::from_fn(|i| object.position[i] - view.eye[i]);
+            let distance = (offset[0] * offset[0] + offset[1] * offset[1] + offset[2] * offset[2])
+                .sqrt()
+                - object.radius;
+            select_lod(
+                &errors,
+                object.radius,
+                distance,
+                view.pixel_scale(),
+                settings.tolerance,
+            )
+        });
+        lists[lod].push(index as u32);
+    }
+    lists
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SelectParams {
+    planes: [[f32; 4]; 6],
+    eye: [f32; 3],
+    pixel_scale: f32,
+    lod_errors: [f32; 4],
+    tolerance: f32,
+    object_count: u32,
+    forced_lod: u32,
+    cull: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: Mat4,
+    show_lod: u32,
+    _padding: [u32; 3],
+}
+
+/// Where the count readback buffer is in its copy/map cycle
+enum Readback {
+    Idle,
+    /// The copy is recorded but not yet submitted
+    Copied,
+    /// Mapping was requested; the callback stores whether it succeeded
+    Mapping(Arc<Mutex<Option<bool>>>),
+}
+
+/// Renderer of the LOD example
+pub struct LodScene {
+    render_pipeline: wgpu::RenderPipeline,
+    select_pipeline: wgpu::ComputePipeline,
+    render_bind_group: wgpu::BindGroup,
+    select_bind_group: wgpu::BindGroup,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    uniform_buffer: wgpu::Buffer,
+    params_buffer: wgpu::Buffer,
+    visible_buffer: wgpu::Buffer,
+    draw_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    readback: Readback,
+    depth_view: wgpu::TextureView,
+    depth_size: (u32, u32),
+    meshes: [LodMesh; LOD_COUNT],
+    objects: Vec<LodObject>,
+    path: SelectionPath,
+    pub settings: LodSettings,
+    /// Tint objects by the mesh they are drawn with
+    pub show_lod: bool,
+    counts: Option<LodCounts>,
+}
+
+impl LodScene {
+    /// Create the renderer drawing into `color_format` targets of `width`x`height`
+    pub fn new(
+        device: &wgpu::Device,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let render_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("LOD Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(LOD_RENDER_SHADER.into()),
+        });
+        let select_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("LOD Select Shader"),
+            source: wgpu::ShaderSource::Wgsl(LOD_SELECT_SHADER.into()),
+        });
+
+        let (vertices, indices, meshes) = lod_meshes();
+        let objects = object_grid(GRID_SIDE);
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("LOD Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("LOD Index Buffer"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let object_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("LOD Object Buffer"),
+            contents: bytemuck::cast_slice(&objects),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("LOD Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("LOD Select Params"),
+            size: std::mem::size_of::<SelectParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let visible_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("LOD Visible Lists"),
+            size: LIST_SIZE * LOD_COUNT as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let draw_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("LOD Indirect Draws"),
+            size: DRAW_ARGS_SIZE * LOD_COUNT as u64,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("LOD Indirect Draws Readback"),
+            size: DRAW_ARGS_SIZE * LOD_COUNT as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let storage_entry =
+            |binding, visibility, read_only, has_dynamic_offset, size| wgpu::BindGroupLayoutEntry {
+                binding,
+                visibility,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only },
+                    has_dynamic_offset,
+                    min_binding_size: wgpu::BufferSize::new(size),
+                },
+                count: None,
+            };
+        let uniform_entry = |visibility| wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let object_size = std::mem::size_of::<LodObject>() as u64;
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let render_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("LOD Render Bind Group Layout"),
+            entries: &[
+                uniform_entry(wgpu::ShaderStages::VERTEX),
+                storage_entry(1, wgpu::ShaderStages::VERTEX, true, false, object_size),
+                storage_entry(2, wgpu::ShaderStages::VERTEX, true, true, 4),
+            ],
+        });
+        let select_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("LOD Select Bind Group Layout"),
+            entries: &[
+                uniform_entry(wgpu::ShaderStages::COMPUTE),
+                storage_entry(1, wgpu::ShaderStages::COMPUTE, true, false, object_size),
+                storage_entry(
+                    2,
+                    wgpu::ShaderStages::COMPUTE,
+                    false,
+                    false,
+                    DRAW_ARGS_SIZE * LOD_COUNT as u64,
+                ),
+                storage_entry(3, wgpu::ShaderStages::COMPUTE, false, false, 4),
+            ],
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let render_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("LOD Render Bind Group"),
+            layout: &render_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: object_buffer.as_entire_binding(),
+                },
+                // One list at a time; the dynamic offset picks the mesh
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &visible_buffer,
+                        offset: 0,
+                        size: wgpu::BufferSize::new(LIST_SIZE),
+                    }),
+                },
+            ],
+        });
+        let select_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("LOD Select Bind Group"),
+            layout: &select_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: object_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: draw_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: visible_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("LOD Render Pipeline Layout"),
+                bind_group_layouts: &[Some(&render_layout)],
+                immediate_size: 0,
+            });
+        let select_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("LOD Select Pipeline Layout"),
+                bind_group_layouts: &[Some(&select_layout)],
+                immediate_size: 0,
+            });
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("LOD Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &render_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<LodVertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Uint32],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let select_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("LOD Select Pipeline"),
+            layout: Some(&select_pipeline_layout),
+            module: &select_shader,
+            entry_point: Some("select_lods"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Self {
+            render_pipeline,
+            select_pipeline,
+            render_bind_group,
+            select_bind_group,
+            vertex_buffer,
+            index_buffer,
+            uniform_buffer,
+            params_buffer,
+            visible_buffer,
+            draw_buffer,
+            readback_buffer,
+            readback: Readback::Idle,
+            depth_view: create_depth_view(device, width, height),
+            depth_size: (width, height),
+            meshes,
+            objects,
+            path: SelectionPath::Cpu,
+            settings: LodSettings::default(),
+            show_lod: true,
+            counts: None,
+        }
+    }
+
+    pub fn meshes(&self) -> &[LodMesh; LOD_COUNT] {
+        &self.meshes
+    }
+
+    pub fn objects(&self) -> &[LodObject] {
+        &self.objects
+    }
+
+    /// Radius of a sphere around the origin containing every object
+    pub fn scene_radius(&self) -> f32 {
+        GRID_SIDE as f32 * SPACING * std::f32::consts::FRAC_1_SQRT_2
+    }
+
+    pub fn set_path(&mut self, path: SelectionPath) {
+        if path != self.path {
+            self.path = path;
+            // The GPU path only learns its counts through the readback
+            self.counts = None;
+        }
+    }
+
+    pub fn path(&self) -> SelectionPath {
+        self.path
+    }
+
+    /// Objects drawn with every mesh in the latest frame; for the GPU path
+    /// the counts arrive a frame or two late
+    pub fn counts(&self) -> Option<LodCounts> {
+        self.counts
+    }
+
+    /// Draw the scene into `target`, a view of a texture of the given size
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        view: &LodView,
+        clear_color: wgpu::Color,
+    ) {
+        if self.depth_size != (width, height) {
+            self.depth_view = create_depth_view(device, width, height);
+            self.depth_size = (width, height);
+        }
+        self.poll_counts(device);
+
+        let tracker = ApiCoverageTracker::global();
+        let uniforms = Uniforms {
+            view_proj: view.view_proj,
+            show_lod: u32::from(self.show_lod),
+            _padding: [0; 3],
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("LOD Encoder"),
+        });
+        let instance_counts = match self.path {
+            SelectionPath::Cpu => {
+                let lists = select_lods(&self.objects, &self.meshes, view, &self.settings);
+                for (lod, list) in lists.iter().enumerate() {
+                    upload_belt::write_buffer(
+                        queue,
+                        &self.visible_buffer,
+                        lod as u64 * LIST_SIZE,
+                        bytemuck::cast_slice(list),
+                    );
+                }
+                let instances = lists.map(|list| list.len() as u32);
+                self.counts = Some(LodCounts {
+                    instances,
+                    culled: self.objects.len() as u32 - instances.iter().sum::<u32>(),
+                });
+                Some(instances)
+            }
+            SelectionPath::Gpu => {
+                self.record_selection(queue, &mut encoder, view);
+                None
+            }
+        };
+
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("LOD Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            for (lod, mesh) in self.meshes.iter().enumerate() {
+                tracker.record(ApiCategory::RenderPass, "set_bind_group");
+                render_pass.set_bind_group(
+                    0,
+                    &self.render_bind_group,
+                    &[(lod as u64 * LIST_SIZE) as wgpu::DynamicOffset],
+                );
+                match instance_counts {
+                    Some(instances) => {
+                        tracker.record(ApiCategory::RenderPass, "draw_indexed");
+                        let indices = mesh.first_index..mesh.first_index + mesh.index_count;
+                        render_pass.draw_indexed(indices, mesh.base_vertex, 0..instances[lod]);
+                    }
+                    None => {
+                        tracker.record(ApiCategory::RenderPass, "draw_indexed_indirect");
+                        render_pass
+                            .draw_indexed_indirect(&self.draw_buffer, lod as u64 * DRAW_ARGS_SIZE);
+                    }
+                }
+            }
+        }
+
+        if self.path == SelectionPath::Gpu && matches!(self.readback, Readback::Idle) {
+            encoder.copy_buffer_to_buffer(
+                &self.draw_buffer,
+                0,
+                &self.readback_buffer,
+                0,
+                DRAW_ARGS_SIZE * LOD_COUNT as u64,
+            );
+            self.readback = Readback::Copied;
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+
+        if matches!(self.readback, Readback::Copied) {
+            let mapped = Arc::new(Mutex::new(None));
+            let callback_mapped = mapped.clone();
+            self.readback_buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    *callback_mapped.lock().unwrap_or_else(|e| e.into_inner()) =
+                        Some(result.is_ok());
+                });
+            self.readback = Readback::Mapping(mapped);
+        }
+    }
+
+    /// Reset the indirect draws and record the selection pass
+    fn record_selection(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &LodView,
+    ) {
+        let draws = self.meshes.map(|mesh| wgpu::util::DrawIndexedIndirectArgs {
+            index_count: mesh.index_count,
+            instance_count: 0,
+            first_index: mesh.first_index,
+            base_vertex: mesh.base_vertex,
+            first_instance: 0,
+        });
+        let draw_bytes: Vec<u8> = draws
+            .iter()
+            .flat_map(|args| args.as_bytes().to_vec())
+            .collect();
+        upload_belt::write_buffer(queue, &self.draw_buffer, 0, &draw_bytes);
+
+        let errors = self.meshes.map(|mesh| mesh.error);
+        let params = SelectParams {
+            planes: frustum_planes(&view.view_proj),
+            eye: view.eye,
+            pixel_scale: view.pixel_scale(),
+            lod_errors: [errors[0], errors[1], errors[2], 0.0],
+            tolerance: self.settings.tolerance,
+            object_count: self.objects.len() as u32,
+            forced_lod: self
+                .settings
+                .forced_lod
+                .map_or(AUTO_LOD, |lod| lod.min(LOD_COUNT - 1) as u32),
+            cull: u32::from(self.settings.cull),
+        };
+        upload_belt::write_buffer(queue, &self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("LOD Select Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.select_pipeline);
+        compute_pass.set_bind_group(0, &self.select_bind_group, &[]);
+        tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+        compute_pass.dispatch_workgroups((self.objects.len() as u32).div_ceil(64), 1, 1);
+    }
+
+    /// Wait until the counts of the last GPU-selected frame have arrived
+    ///
+    /// Meant for tests; the frame loop picks the counts up without blocking.
+    pub fn wait_for_counts(&mut self, device: &wgpu::Device) -> bool {
+        if !matches!(self.readback, Readback::Mapping(_)) {
+            return false;
+        }
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+        self.poll_counts(device)
+    }
+
+    /// Collect finished count readbacks without blocking
+    fn poll_counts(&mut self, device: &wgpu::Device) -> bool {
+        let Readback::Mapping(mapped) = &self.readback else {
+            return false;
+        };
+        let _ = device.poll(wgpu::PollType::Poll);
+        let Some(success) = *mapped.lock().unwrap_or_else(|e| e.into_inner()) else {
+            return false;
+        };
+        self.readback = Readback::Idle;
+        if !success {
+            log::warn!("Failed to map the LOD draw readback buffer");
+            return false;
+        }
+
+        let instances: [u32; LOD_COUNT] = {
+            let data = self.readback_buffer.slice(..).get_mapped_range();
+            let words: &[u32] = bytemuck::cast_slice(&data);
+            // instance_count is the second word of every DrawIndexedIndirectArgs
+            std::array::from_fn(|lod| words[lod * 5 + 1])
+        };
+        self.readback_buffer.unmap();
+        if self.path == SelectionPath::Gpu {
+            self.counts = Some(LodCounts {
+                instances,
+                culled: self.objects.len() as u32 - instances.iter().sum::<u32>(),
+            });
+        }
+        true
+    }
+}
+
+fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("LOD Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_utils::{cross, dot, look_at, mat4_mul, perspective};
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        validate(LOD_SELECT_SHADER);
+        validate(LOD_RENDER_SHADER);
+        assert!(LOD_SELECT_SHADER.contains(&format!("const LOD_COUNT: u32 = {}u;", LOD_COUNT)));
+        assert!(LOD_SELECT_SHADER.contains(&format!("const MAX_OBJECTS: u32 = {}u;", MAX_OBJECTS)));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<LodObject>(), 32);
+        assert_eq!(std::mem::size_of::<SelectParams>(), 144);
+        assert_eq!(std::mem::size_of::<Uniforms>(), 80);
+        assert_eq!(DRAW_ARGS_SIZE, 20);
+        assert_eq!(LIST_SIZE % 256, 0);
+    }
+
+    #[test]
+    fn test_icosphere_is_closed_and_outward() {
+        for subdivisions in 0..3 {
+            let (positions, indices) = icosphere(subdivisions);
+            assert_eq!(indices.len() / 3, 20 * 4usize.pow(subdivisions));
+            // Euler characteristic of a sphere: V - E + F = 2
+            let faces = indices.len() / 3;
+            assert_eq!(positions.len() + faces - faces * 3 / 2, 2);
+            for triangle in indices.chunks_exact(3) {
+                let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+                let normal = cross(
+                    std::array::from_fn(|i| b[i] - a[i]),
+                    std::array::from_fn(|i| c[i] - a[i]),
+                );
+                assert!(dot(normal, a) > 0.0, "triangles face outwards");
+            }
+        }
+    }
+
+    #[test]
+    fn test_lod_meshes_get_coarser() {
+        let (vertices, indices, meshes) = lod_meshes();
+        assert_eq!(meshes.map(|mesh| mesh.triangles()), [1280, 320, 80]);
+        assert!(meshes[0].error < meshes[1].error && meshes[1].error < meshes[2].error);
+        assert!(meshes[2].error < 0.1);
+        let last = meshes[LOD_COUNT - 1];
+        assert_eq!(
+            (last.first_index + last.index_count) as usize,
+            indices.len()
+        );
+        assert!(vertices.iter().all(|v| v.lod < LOD_COUNT as u32));
+        assert!(vertices.len() < u16::MAX as usize);
+    }
+
+    #[test]
+    fn test_select_lod_coarsens_with_distance() {
+        let errors = [0.001, 0.01, 0.05];
+        let lod_at = |distance| select_lod(&errors, 1.0, distance, 500.0, 1.0);
+        assert_eq!(lod_at(1.0), 0);
+        assert_eq!(lod_at(10.0), 1);
+        assert_eq!(lod_at(100.0), 2);
+        let mut previous = 0;
+        for distance in (1..200).map(|d| d as f32) {
+            let lod = lod_at(distance);
+            assert!(lod >= previous);
+            previous = lod;
+        }
+        // A looser tolerance allows coarser meshes
+        assert_eq!(select_lod(&errors, 1.0, 10.0, 500.0, 30.0), 2);
+    }
+
+    #[test]
+    fn test_select_lods_culls_and_forces() {
+        let (_, _, meshes) = lod_meshes();
+        let objects = object_grid(8);
+        let eye = [0.0, 5.0, 20.0];
+        let view = LodView {
+            view_proj: mat4_mul(
+                &perspective(1.0, 1.0, 0.1, 100.0),
+                &look_at(eye, [0.0; 3], [0.0, 1.0, 0.0]),
+            ),
+            eye,
+            fov_y: 1.0,
+            viewport_height: 512,
+        };
+        let settings = LodSettings::default();
+        let lists = select_lods(&objects, &meshes, &view, &settings);
+        let drawn: usize = lists.iter().map(Vec::len).sum();
+        assert!(drawn > 0 && drawn <= objects.len());
+
+        let uncull = LodSettings {
+            cull: false,
+            ..settings
+        };
+        let lists = select_lods(&objects, &meshes, &view, &uncull);
+        assert_eq!(lists.iter().map(Vec::len).sum::<usize>(), objects.len());
+
+        let forced = LodSettings {
+            forced_lod: Some(2),
+            cull: false,
+            ..settings
+        };
+        let lists = select_lods(&objects, &meshes, &view, &forced);
+        assert_eq!(lists[2].len(), objects.len());
+    }
+
+    #[test]
+    fn test_triangle_counts() {
+        let (_, _, meshes) = lod_meshes();
+        let counts = LodCounts {
+            instances: [2, 0, 10],
+            culled: 5,
+        };
+        assert_eq!(counts.triangles(&meshes), [2560, 0, 800]);
+    }
+}
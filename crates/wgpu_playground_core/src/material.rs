@@ -0,0 +1,1335 @@
+//! Physically based materials with the metallic-roughness model
+//!
+//! [`PbrMaterial`] follows glTF 2.0: a base color, metalness and roughness,
+//! each optionally multiplied by a texture, plus a tangent-space normal map,
+//! an ambient occlusion map and an emissive color. [`PbrRenderer`] draws a
+//! loaded model with them:
+//!
+//! - One directional light, shaded with the GGX distribution, the
+//!   height-correlated Smith visibility term and Schlick's Fresnel
+//! - Optional image-based lighting from a procedural sky, using the
+//!   split-sum approximation with an analytic environment BRDF
+//! - Exposure and a filmic tone curve, written to an sRGB target
+//!
+//! Normal maps use a tangent frame built from screen-space derivatives, so
+//! models don't need to ship tangents. The frame, the normals and the
+//! vertex tangents can be inspected with the views of [`TangentDebug`].
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::camera::Camera;
+use crate::math_utils::{mat4_inverse, Mat4, MAT4_IDENTITY};
+use crate::model_loader::{self, ModelData, ModelLoadError, ModelTexture, ModelVertex};
+use crate::tangent_debug::{self, DebugView, GlyphVertex, TangentDebug};
+use crate::upload_belt;
+use std::ops::Range;
+use wgpu::util::DeviceExt;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub const PBR_SHADER: &str = r#"// Metallic-roughness PBR
+// Group 0 holds the camera, light and environment, group 1 one material.
+
+struct Frame {
+    view_proj: mat4x4<f32>,
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+    camera_position: vec4<f32>,
+    // Unit vector towards the light in xyz
+    light_direction: vec4<f32>,
+    // Color times intensity in rgb
+    light_color: vec4<f32>,
+    // x: image-based lighting on (1) or off (0), y: its intensity, z: exposure
+    environment: vec4<f32>,
+    // x: one of the *_VIEW constants, y: normal map strength, z: glyph length
+    debug: vec4<f32>,
+}
+
+struct Material {
+    base_color: vec4<f32>,
+    emissive: vec3<f32>,
+    metallic: f32,
+    roughness: f32,
+    normal_scale: f32,
+    occlusion_strength: f32,
+    // Which maps are bound and enabled, see the *_MAP bits
+    map_flags: u32,
+}
+
+@group(0) @binding(0) var<uniform> frame: Frame;
+
+@group(1) @binding(0) var<uniform> material: Material;
+@group(1) @binding(1) var base_color_map: texture_2d<f32>;
+@group(1) @binding(2) var normal_map: texture_2d<f32>;
+@group(1) @binding(3) var metallic_roughness_map: texture_2d<f32>;
+@group(1) @binding(4) var occlusion_map: texture_2d<f32>;
+@group(1) @binding(5) var material_sampler: sampler;
+
+const PI: f32 = 3.14159265;
+const BASE_COLOR_MAP: u32 = 1u;
+const NORMAL_MAP: u32 = 2u;
+const METALLIC_ROUGHNESS_MAP: u32 = 4u;
+const OCCLUSION_MAP: u32 = 8u;
+const SHADED_VIEW: u32 = 0u;
+const NORMALS_VIEW: u32 = 1u;
+const TANGENTS_VIEW: u32 = 2u;
+const BITANGENTS_VIEW: u32 = 3u;
+const MAPPED_NORMALS_VIEW: u32 = 4u;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    let world = frame.model * vec4<f32>(in.position, 1.0);
+    var out: VertexOutput;
+    out.clip_position = frame.view_proj * world;
+    out.world_position = world.xyz;
+    out.normal = (frame.normal_matrix * vec4<f32>(in.normal, 0.0)).xyz;
+    out.uv = in.uv;
+    return out;
+}
+
+fn has_map(map: u32) -> bool {
+    return (material.map_flags & map) != 0u;
+}
+
+// Tangent frame around the normal n, with the tangent pointing along +u and
+// the bitangent along -v as in glTF, flipped where the UVs are mirrored
+fn tangent_frame(n: vec3<f32>, position: vec3<f32>, uv: vec2<f32>) -> mat3x3<f32> {
+    let uv_dx = dpdx(uv);
+    let uv_dy = dpdy(uv);
+    let det = uv_dx.x * uv_dy.y - uv_dy.x * uv_dx.y;
+    let safe_det = select(det, 1.0, abs(det) < 1e-12);
+    let t_raw = (uv_dy.y * dpdx(position) - uv_dx.y * dpdy(position)) / safe_det;
+    let b_raw = (uv_dx.x * dpdy(position) - uv_dy.x * dpdx(position)) / safe_det;
+    var t = t_raw - n * dot(n, t_raw);
+    // Degenerate UVs leave no tangent; any perpendicular will do
+    if dot(t, t) < 1e-12 {
+        t = cross(n, select(vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 1.0, 0.0), abs(n.x) > 0.9));
+    }
+    t = normalize(t);
+    let b = cross(n, t) * select(1.0, -1.0, dot(cross(n, t), b_raw) > 0.0);
+    return mat3x3<f32>(t, b, n);
+}
+
+// Apply a tangent-space normal map sample
+fn perturb_normal(tbn: mat3x3<f32>, sample: vec3<f32>) -> vec3<f32> {
+    let scale = material.normal_scale * frame.debug.y;
+    let tangent_normal = vec3<f32>((sample.xy * 2.0 - 1.0) * scale, sample.z * 2.0 - 1.0);
+    return normalize(tbn * tangent_normal);
+}
+
+// The target is sRGB; decoding first makes it store the values as they are
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    return select(pow((c + 0.055) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
+}
+
+// A unit direction as a color, each axis mapped from [-1, 1] to [0, 1]
+fn direction_color(direction: vec3<f32>) -> vec4<f32> {
+    return vec4<f32>(srgb_to_linear(direction * 0.5 + 0.5), 1.0);
+}
+
+fn distribution_ggx(n_dot_h: f32, alpha: f32) -> f32 {
+    let a2 = alpha * alpha;
+    let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    return a2 / (PI * d * d);
+}
+
+// Height-correlated Smith term, folded with the BRDF's 4 N.L N.V divisor
+fn visibility_smith(n_dot_v: f32, n_dot_l: f32, alpha: f32) -> f32 {
+    let a2 = alpha * alpha;
+    let gv = n_dot_l * sqrt(n_dot_v * n_dot_v * (1.0 - a2) + a2);
+    let gl = n_dot_v * sqrt(n_dot_l * n_dot_l * (1.0 - a2) + a2);
+    return 0.5 / max(gv + gl, 1e-5);
+}
+
+fn fresnel_schlick(f0: vec3<f32>, v_dot_h: f32) -> vec3<f32> {
+    return f0 + (1.0 - f0) * pow(1.0 - v_dot_h, 5.0);
+}
+
+// Karis' analytic fit of the split-sum environment BRDF
+fn environment_brdf(f0: vec3<f32>, roughness: f32, n_dot_v: f32) -> vec3<f32> {
+    let c0 = vec4<f32>(-1.0, -0.0275, -0.572, 0.022);
+    let c1 = vec4<f32>(1.0, 0.0425, 1.04, -0.04);
+    let r = roughness * c0 + c1;
+    let a004 = min(r.x * r.x, exp2(-9.28 * n_dot_v)) * r.x + r.y;
+    let ab = vec2<f32>(-1.04, 1.04) * a004 + r.zw;
+    return f0 * ab.x + ab.y;
+}
+
+// Procedural sky standing in for a prefiltered environment map: rougher
+// lookups see a flatter, more averaged sky
+fn sky(direction: vec3<f32>, roughness: f32) -> vec3<f32> {
+    let zenith = vec3<f32>(0.25, 0.45, 0.85);
+    let horizon = vec3<f32>(0.8, 0.85, 0.9);
+    let ground = vec3<f32>(0.25, 0.22, 0.2);
+    let y = direction.y;
+    let sharp = select(
+        mix(horizon, ground, sqrt(max(-y, 0.0))),
+        mix(horizon, zenith, sqrt(max(y, 0.0))),
+        y >= 0.0,
+    );
+    let average = (zenith + 2.0 * horizon + ground) * 0.25;
+    return mix(sharp, average, roughness * roughness);
+}
+
+@fragment
+fn fs_main(in: VertexOutput, @builtin(front_facing) front_facing: bool) -> @location(0) vec4<f32> {
+    // Sample every map up front so derivatives stay in uniform control flow
+    let base_sample = textureSample(base_color_map, material_sampler, in.uv);
+    let normal_sample = textureSample(normal_map, material_sampler, in.uv).xyz;
+    let metallic_roughness_sample = textureSample(metallic_roughness_map, material_sampler, in.uv);
+    let occlusion_sample = textureSample(occlusion_map, material_sampler, in.uv).r;
+
+    // Back faces are lit as seen, so open and double-sided meshes look right
+    let geometric_normal = normalize(in.normal) * select(-1.0, 1.0, front_facing);
+    let tbn = tangent_frame(geometric_normal, in.world_position, in.uv);
+    let mapped_normal = perturb_normal(tbn, normal_sample);
+
+    switch u32(frame.debug.x) {
+        case NORMALS_VIEW: {
+            return direction_color(geometric_normal);
+        }
+        case TANGENTS_VIEW: {
+            return direction_color(tbn[0]);
+        }
+        case BITANGENTS_VIEW: {
+            return direction_color(tbn[1]);
+        }
+        case MAPPED_NORMALS_VIEW: {
+            return direction_color(select(geometric_normal, mapped_normal, has_map(NORMAL_MAP)));
+        }
+        default: {}
+    }
+
+    var base_color = material.base_color;
+    if has_map(BASE_COLOR_MAP) {
+        base_color *= base_sample;
+    }
+    var n = geometric_normal;
+    if has_map(NORMAL_MAP) {
+        n = mapped_normal;
+    }
+    var metallic = material.metallic;
+    var roughness = material.roughness;
+    if has_map(METALLIC_ROUGHNESS_MAP) {
+        roughness *= metallic_roughness_sample.g;
+        metallic *= metallic_roughness_sample.b;
+    }
+    metallic = clamp(metallic, 0.0, 1.0);
+    roughness = clamp(roughness, 0.04, 1.0);
+    var occlusion = 1.0;
+    if has_map(OCCLUSION_MAP) {
+        occlusion = 1.0 + material.occlusion_strength * (occlusion_sample - 1.0);
+    }
+
+    let v = normalize(frame.camera_position.xyz - in.world_position);
+    let l = normalize(frame.light_direction.xyz);
+    let h = normalize(v + l);
+    let n_dot_v = max(dot(n, v), 1e-4);
+    let n_dot_l = max(dot(n, l), 0.0);
+    let n_dot_h = max(dot(n, h), 0.0);
+    let v_dot_h = max(dot(v, h), 0.0);
+    let alpha = roughness * roughness;
+
+    let f0 = mix(vec3<f32>(0.04), base_color.rgb, metallic);
+    let diffuse_color = base_color.rgb * (1.0 - metallic);
+    let fresnel = fresnel_schlick(f0, v_dot_h);
+    let specular = fresnel * distribution_ggx(n_dot_h, alpha) * visibility_smith(n_dot_v, n_dot_l, alpha);
+    let diffuse = (1.0 - fresnel) * diffuse_color / PI;
+    var color = (diffuse + specular) * frame.light_color.rgb * n_dot_l;
+
+    if frame.environment.x > 0.5 {
+        let irradiance = sky(n, 1.0);
+        let prefiltered = sky(reflect(-v, n), roughness);
+        let ambient = diffuse_color * irradiance + prefiltered * environment_brdf(f0, roughness, n_dot_v);
+        color += ambient * frame.environment.y * occlusion;
+    } else {
+        color += 0.03 * base_color.rgb * occlusion;
+    }
+    color += material.emissive;
+
+    // Exposure, then Narkowicz's fit of the ACES filmic curve
+    let x = color * frame.environment.z;
+    let mapped = clamp((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14), vec3<f32>(0.0), vec3<f32>(1.0));
+    return vec4<f32>(mapped, 1.0);
+}
+"#;
+
+/// Line glyphs along vertex tangent frames, drawn over the model by
+/// [`PbrRenderer`] with the same group 0
+pub const GLYPH_SHADER: &str = r#"// Tangent frame glyphs
+// Frame matches the PBR shader's
+
+struct Frame {
+    view_proj: mat4x4<f32>,
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+    camera_position: vec4<f32>,
+    light_direction: vec4<f32>,
+    light_color: vec4<f32>,
+    environment: vec4<f32>,
+    // z: glyph length as a fraction of the offsets
+    debug: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> frame: Frame;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) offset: vec3<f32>,
+    @location(2) color: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    let world = frame.model * vec4<f32>(in.position + in.offset * frame.debug.z, 1.0);
+    var out: VertexOutput;
+    out.clip_position = frame.view_proj * world;
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+"#;
+
+/// Texture slots of a [`PbrMaterial`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialMap {
+    BaseColor,
+    Normal,
+    /// Roughness in the green channel, metalness in blue
+    MetallicRoughness,
+    /// Ambient occlusion in the red channel
+    Occlusion,
+}
+
+impl MaterialMap {
+    pub const ALL: [MaterialMap; 4] = [
+        MaterialMap::BaseColor,
+        MaterialMap::Normal,
+        MaterialMap::MetallicRoughness,
+        MaterialMap::Occlusion,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MaterialMap::BaseColor => "Base color",
+            MaterialMap::Normal => "Normal",
+            MaterialMap::MetallicRoughness => "Metallic-roughness",
+            MaterialMap::Occlusion => "Occlusion",
+        }
+    }
+
+    /// Bit in the shader's `map_flags`
+    pub fn flag(self) -> u32 {
+        1 << self as u32
+    }
+
+    /// Color maps are sampled as sRGB, data maps as linear
+    pub fn is_srgb(self) -> bool {
+        self == MaterialMap::BaseColor
+    }
+}
+
+/// A texture slot: the model texture it uses, if any, and whether the
+/// editor left it switched on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapSlot {
+    pub texture: Option<usize>,
+    pub enabled: bool,
+}
+
+impl MapSlot {
+    pub fn new(texture: Option<usize>) -> Self {
+        Self {
+            texture,
+            enabled: true,
+        }
+    }
+
+    /// The texture to sample, if the slot has one and is enabled
+    pub fn active(&self) -> Option<usize> {
+        self.texture.filter(|_| self.enabled)
+    }
+}
+
+/// Metallic-roughness material parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct PbrMaterial {
+    pub name: String,
+    /// Linear RGBA multiplied with the base color map
+    pub base_color: [f32; 4],
+    pub metallic: f32,
+    /// Perceptual roughness; the GGX alpha is its square
+    pub roughness: f32,
+    /// Linear RGB added after lighting
+    pub emissive: [f32; 3],
+    pub normal_scale: f32,
+    pub occlusion_strength: f32,
+    /// Texture slots in [`MaterialMap::ALL`] order
+    pub maps: [MapSlot; 4],
+}
+
+impl Default for PbrMaterial {
+    /// Rough white plastic
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            base_color: [1.0; 4],
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive: [0.0; 3],
+            normal_scale: 1.0,
+            occlusion_strength: 1.0,
+            maps: [MapSlot::new(None); 4],
+        }
+    }
+}
+
+impl From<&model_loader::Material> for PbrMaterial {
+    fn from(material: &model_loader::Material) -> Self {
+        Self {
+            name: material.name.clone(),
+            base_color: material.diffuse_color,
+            metallic: material.metallic,
+            roughness: material.roughness,
+            emissive: material.emissive,
+            normal_scale: material.normal_scale,
+            occlusion_strength: material.occlusion_strength,
+            maps: [
+                MapSlot::new(material.base_color_texture),
+                MapSlot::new(material.normal_texture),
+                MapSlot::new(material.metallic_roughness_texture),
+                MapSlot::new(material.occlusion_texture),
+            ],
+        }
+    }
+}
+
+impl PbrMaterial {
+    pub fn map(&self, map: MaterialMap) -> &MapSlot {
+        &self.maps[map as usize]
+    }
+
+    pub fn map_mut(&mut self, map: MaterialMap) -> &mut MapSlot {
+        &mut self.maps[map as usize]
+    }
+
+    /// Shader flags of the maps that are bound and enabled
+    pub fn map_flags(&self) -> u32 {
+        MaterialMap::ALL
+            .into_iter()
+            .filter(|&map| self.map(map).active().is_some())
+            .fold(0, |flags, map| flags | map.flag())
+    }
+
+    fn uniforms(&self) -> MaterialUniforms {
+        MaterialUniforms {
+            base_color: self.base_color,
+            emissive: self.emissive,
+            metallic: self.metallic,
+            roughness: self.roughness,
+            normal_scale: self.normal_scale,
+            occlusion_strength: self.occlusion_strength,
+            map_flags: self.map_flags(),
+        }
+    }
+}
+
+/// The directional light and environment shared by all materials
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lighting {
+    /// Degrees around the Y axis, 0 lighting from +Z
+    pub light_azimuth: f32,
+    /// Degrees above the horizon
+    pub light_elevation: f32,
+    pub light_color: [f32; 3],
+    pub light_intensity: f32,
+    /// Add image-based lighting from the procedural sky
+    pub ibl: bool,
+    pub ibl_intensity: f32,
+    pub exposure: f32,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Self {
+            light_azimuth: 30.0,
+            light_elevation: 45.0,
+            light_color: [1.0, 0.96, 0.9],
+            light_intensity: 3.0,
+            ibl: true,
+            ibl_intensity: 1.0,
+            exposure: 1.0,
+        }
+    }
+}
+
+impl Lighting {
+    /// Unit vector pointing towards the light
+    pub fn light_direction(&self) -> [f32; 3] {
+        let (sin_azimuth, cos_azimuth) = self.light_azimuth.to_radians().sin_cos();
+        let (sin_elevation, cos_elevation) = self.light_elevation.to_radians().sin_cos();
+        [
+            cos_elevation * sin_azimuth,
+            sin_elevation,
+            cos_elevation * cos_azimuth,
+        ]
+    }
+
+    /// Light direction, color, environment and exposure sliders
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::Slider::new(&mut self.light_azimuth, -180.0..=180.0).text("Light azimuth (°)"),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.light_elevation, -90.0..=90.0).text("Light elevation (°)"),
+        );
+        ui.horizontal(|ui| {
+            ui.label("Light color:");
+            ui.color_edit_button_rgb(&mut self.light_color);
+        });
+        ui.add(egui::Slider::new(&mut self.light_intensity, 0.0..=10.0).text("Light intensity"));
+        ui.checkbox(&mut self.ibl, "Image-based lighting (procedural sky)");
+        ui.add_enabled(
+            self.ibl,
+            egui::Slider::new(&mut self.ibl_intensity, 0.0..=4.0).text("Environment intensity"),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.exposure, 0.1..=8.0)
+                .logarithmic(true)
+                .text("Exposure"),
+        );
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct FrameUniforms {
+    view_proj: Mat4,
+    model: Mat4,
+    normal_matrix: Mat4,
+    camera_position: [f32; 4],
+    light_direction: [f32; 4],
+    light_color: [f32; 4],
+    environment: [f32; 4],
+    debug: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct MaterialUniforms {
+    base_color: [f32; 4],
+    emissive: [f32; 3],
+    metallic: f32,
+    roughness: f32,
+    normal_scale: f32,
+    occlusion_strength: f32,
+    map_flags: u32,
+}
+
+/// Inverse transpose of the model matrix, for transforming normals
+fn normal_matrix(model: &Mat4) -> Mat4 {
+    let inverse = mat4_inverse(model).unwrap_or(MAT4_IDENTITY);
+    std::array::from_fn(|column| std::array::from_fn(|row| inverse[row][column]))
+}
+
+/// Center and radius of a sphere around every vertex of a model
+pub fn bounding_sphere(model: &ModelData) -> ([f32; 3], f32) {
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for vertex in model.meshes.iter().flat_map(|mesh| &mesh.vertices) {
+        for i in 0..3 {
+            min[i] = min[i].min(vertex.position[i]);
+            max[i] = max[i].max(vertex.position[i]);
+        }
+    }
+    if min[0] > max[0] {
+        return ([0.0; 3], 1.0);
+    }
+    let center = std::array::from_fn(|i| (min[i] + max[i]) / 2.0);
+    let radius = (0..3)
+        .map(|i| (max[i] - center[i]).powi(2))
+        .sum::<f32>()
+        .sqrt();
+    (center, radius)
+}
+
+/// A model uploaded for [`PbrRenderer`], with editable materials
+pub struct GpuModel {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    /// Index range and material of every mesh
+    draws: Vec<(Range<u32>, usize)>,
+    /// One per model material, plus a default for meshes without one
+    pub materials: Vec<PbrMaterial>,
+    material_buffers: Vec<wgpu::Buffer>,
+    material_bind_groups: Vec<wgpu::BindGroup>,
+    /// Size of each texture the materials can use
+    texture_sizes: Vec<(u32, u32)>,
+    /// Line list of [`GlyphVertex`]es along the vertex tangent frames
+    glyph_buffer: wgpu::Buffer,
+    glyph_vertex_count: u32,
+}
+
+impl GpuModel {
+    /// Width and height of a model texture
+    pub fn texture_size(&self, index: usize) -> Option<(u32, u32)> {
+        self.texture_sizes.get(index).copied()
+    }
+
+    /// Number of draw calls per frame
+    pub fn draw_count(&self) -> usize {
+        self.draws.len()
+    }
+}
+
+/// Renderer drawing models with [`PBR_SHADER`]
+pub struct PbrRenderer {
+    pipeline: wgpu::RenderPipeline,
+    frame_buffer: wgpu::Buffer,
+    frame_bind_group: wgpu::BindGroup,
+    material_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    /// Bound to slots without a texture
+    fallback_view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+    depth_size: (u32, u32),
+    glyph_pipeline: wgpu::RenderPipeline,
+    /// Whether the next frames draw glyphs, set by [`update`](Self::update)
+    show_glyphs: bool,
+}
+
+impl PbrRenderer {
+    /// Create the renderer drawing into `color_format` targets, which should
+    /// be sRGB for correct output
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("PBR Shader"),
+            source: wgpu::ShaderSource::Wgsl(PBR_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let frame_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("PBR Frame Uniform Buffer"),
+            size: std::mem::size_of::<FrameUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let frame_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("PBR Frame Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let material_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("PBR Material Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                texture_entry(1),
+                texture_entry(2),
+                texture_entry(3),
+                texture_entry(4),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let frame_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("PBR Frame Bind Group"),
+            layout: &frame_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: frame_buffer.as_entire_binding(),
+            }],
+        });
+
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("PBR Material Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let fallback_view = create_texture(device, queue, &ModelTexture::white(), false)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("PBR Pipeline Layout"),
+            bind_group_layouts: &[Some(&frame_layout), Some(&material_layout)],
+            immediate_size: 0,
+        });
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("PBR Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[ModelVertex::layout()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            // Imported meshes don't agree on winding or sidedness, so
+            // nothing is culled and the shader flips back-face normals
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let glyph_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Tangent Glyph Shader"),
+            source: wgpu::ShaderSource::Wgsl(GLYPH_SHADER.into()),
+        });
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let glyph_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Tangent Glyph Pipeline Layout"),
+            bind_group_layouts: &[Some(&frame_layout)],
+            immediate_size: 0,
+        });
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let glyph_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Tangent Glyph Pipeline"),
+            layout: Some(&glyph_layout),
+            vertex: wgpu::VertexState {
+                module: &glyph_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphVertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x3,
+                        1 => Float32x3,
+                        2 => Float32x3
+                    ],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &glyph_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            // Lines start on the surface, so they pass where equally deep
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::LessEqual),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Self {
+            pipeline,
+            frame_buffer,
+            frame_bind_group,
+            material_layout,
+            sampler,
+            fallback_view,
+            depth_view: create_depth_view(device, 1, 1),
+            depth_size: (1, 1),
+            glyph_pipeline,
+            show_glyphs: false,
+        }
+    }
+
+    /// Upload a model's geometry, textures and materials
+    pub fn upload(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        model: &ModelData,
+    ) -> Result<GpuModel, ModelLoadError> {
+        let (vertex_buffer, index_buffer) = model.create_buffers(device)?;
+
+        let mut materials: Vec<PbrMaterial> =
+            model.materials.iter().map(PbrMaterial::from).collect();
+        let default_material = materials.len();
+        materials.push(PbrMaterial::default());
+
+        // Meshes are concatenated in order by `create_buffers`
+        let mut draws = Vec::with_capacity(model.meshes.len());
+        let mut first_index = 0;
+        for mesh in &model.meshes {
+            let end = first_index + mesh.indices.len() as u32;
+            let material = mesh
+                .material_index
+                .filter(|&index| index < default_material)
+                .unwrap_or(default_material);
+            draws.push((first_index..end, material));
+            first_index = end;
+        }
+
+        // A texture referenced as a base color is stored as sRGB
+        let srgb: Vec<bool> = (0..model.textures.len())
+            .map(|index| {
+                materials
+                    .iter()
+                    .any(|material| material.map(MaterialMap::BaseColor).texture == Some(index))
+            })
+            .collect();
+        let views: Vec<wgpu::TextureView> = model
+            .textures
+            .iter()
+            .zip(&srgb)
+            .map(|(texture, &srgb)| {
+                create_texture(device, queue, texture, srgb)
+                    .create_view(&wgpu::TextureViewDescriptor::default())
+            })
+            .collect();
+
+        let tracker = ApiCoverageTracker::global();
+        let mut material_buffers = Vec::with_capacity(materials.len());
+        let mut material_bind_groups = Vec::with_capacity(materials.len());
+        for material in &materials {
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("PBR Material Uniform Buffer"),
+                contents: bytemuck::bytes_of(&material.uniforms()),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+            let view = |map: MaterialMap| {
+                material
+                    .map(map)
+                    .texture
+                    .and_then(|index| views.get(index))
+                    .unwrap_or(&self.fallback_view)
+            };
+            tracker.record(ApiCategory::BindGroup, "create_bind_group");
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("PBR Material Bind Group"),
+                layout: &self.material_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(view(MaterialMap::BaseColor)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(view(MaterialMap::Normal)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(view(
+                            MaterialMap::MetallicRoughness,
+                        )),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(view(MaterialMap::Occlusion)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            });
+            material_buffers.push(buffer);
+            material_bind_groups.push(bind_group);
+        }
+
+        let (_, radius) = bounding_sphere(model);
+        let glyphs = tangent_debug::glyph_lines(model, radius);
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let glyph_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Tangent Glyph Vertex Buffer"),
+            contents: bytemuck::cast_slice(&glyphs),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        Ok(GpuModel {
+            vertex_buffer,
+            index_buffer,
+            draws,
+            materials,
+            material_buffers,
+            material_bind_groups,
+            texture_sizes: model
+                .textures
+                .iter()
+                .map(|texture| (texture.width, texture.height))
+                .collect(),
+            glyph_buffer,
+            glyph_vertex_count: glyphs.len() as u32,
+        })
+    }
+
+    /// Update the camera, model transform, lighting and debug view for the
+    /// next frame
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        camera: &Camera,
+        aspect: f32,
+        model: &Mat4,
+        lighting: &Lighting,
+        debug: &TangentDebug,
+    ) {
+        let eye = camera.eye();
+        let [x, y, z] = lighting.light_direction();
+        let light = lighting.light_color.map(|c| c * lighting.light_intensity);
+        let uniforms = FrameUniforms {
+            view_proj: camera.view_projection(aspect),
+            model: *model,
+            normal_matrix: normal_matrix(model),
+            camera_position: [eye[0], eye[1], eye[2], 1.0],
+            light_direction: [x, y, z, 0.0],
+            light_color: [light[0], light[1], light[2], 1.0],
+            environment: [
+                f32::from(u8::from(lighting.ibl)),
+                lighting.ibl_intensity,
+                lighting.exposure,
+                0.0,
+            ],
+            debug: debug.uniforms(),
+        };
+        self.show_glyphs = debug.glyphs;
+        upload_belt::write_buffer(queue, &self.frame_buffer, 0, bytemuck::bytes_of(&uniforms));
+    }
+
+    /// Draw `model` into `target`, a view of a texture of the given size,
+    /// picking up any edits to its materials
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        model: &GpuModel,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        if self.depth_size != (width, height) {
+            self.depth_view = create_depth_view(device, width, height);
+            self.depth_size = (width, height);
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        for (material, buffer) in model.materials.iter().zip(&model.material_buffers) {
+            upload_belt::write_buffer(queue, buffer, 0, bytemuck::bytes_of(&material.uniforms()));
+        }
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("PBR Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("PBR Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            tracker.record(ApiCategory::RenderPass, "set_pipeline");
+            pass.set_pipeline(&self.pipeline);
+            tracker.record(ApiCategory::RenderPass, "set_bind_group");
+            pass.set_bind_group(0, &self.frame_bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+            pass.set_vertex_buffer(0, model.vertex_buffer.slice(..));
+            tracker.record(ApiCategory::RenderPass, "set_index_buffer");
+            pass.set_index_buffer(model.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            for (indices, material) in &model.draws {
+                tracker.record(ApiCategory::RenderPass, "set_bind_group");
+                pass.set_bind_group(1, &model.material_bind_groups[*material], &[]);
+                tracker.record(ApiCategory::RenderPass, "draw_indexed");
+                pass.draw_indexed(indices.clone(), 0, 0..1);
+            }
+            if self.show_glyphs && model.glyph_vertex_count > 0 {
+                tracker.record(ApiCategory::RenderPass, "set_pipeline");
+                pass.set_pipeline(&self.glyph_pipeline);
+                tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+                pass.set_vertex_buffer(0, model.glyph_buffer.slice(..));
+                tracker.record(ApiCategory::RenderPass, "draw");
+                pass.draw(0..model.glyph_vertex_count, 0..1);
+            }
+        }
+        upload_belt::submit(queue, Some(encoder.finish()));
+    }
+}
+
+fn create_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &ModelTexture,
+    srgb: bool,
+) -> wgpu::Texture {
+    let size = wgpu::Extent3d {
+        width: texture.width.max(1),
+        height: texture.height.max(1),
+        depth_or_array_layers: 1,
+    };
+    let tracker = ApiCoverageTracker::global();
+    tracker.record(ApiCategory::Texture, "create_texture");
+    let gpu_texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("PBR Material Texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: if srgb {
+            wgpu::TextureFormat::Rgba8UnormSrgb
+        } else {
+            wgpu::TextureFormat::Rgba8Unorm
+        },
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    tracker.record(ApiCategory::Queue, "write_texture");
+    queue.write_texture(
+        gpu_texture.as_image_copy(),
+        &texture.rgba,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * size.width),
+            rows_per_image: Some(size.height),
+        },
+        size,
+    );
+    gpu_texture
+}
+
+fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("PBR Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Editor for the materials of a loaded model
+#[derive(Debug, Clone, Default)]
+pub struct MaterialEditor {
+    selected: usize,
+}
+
+impl MaterialEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Material picker, factor sliders and texture slot toggles
+    pub fn ui(&mut self, ui: &mut egui::Ui, model: &mut GpuModel) {
+        if model.materials.is_empty() {
+            return;
+        }
+        self.selected = self.selected.min(model.materials.len() - 1);
+
+        let names: Vec<String> = model
+            .materials
+            .iter()
+            .enumerate()
+            .map(|(i, material)| format!("{}: {}", i, material.name))
+            .collect();
+        egui::ComboBox::from_label("Material")
+            .selected_text(names[self.selected].as_str())
+            .show_ui(ui, |ui| {
+                for (i, name) in names.iter().enumerate() {
+                    ui.selectable_value(&mut self.selected, i, name.as_str());
+                }
+            });
+
+        let texture_sizes = model.texture_sizes.clone();
+        let material = &mut model.materials[self.selected];
+        egui::Grid::new("pbr_material")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                ui.label("Base color:");
+                ui.color_edit_button_rgba_unmultiplied(&mut material.base_color);
+                ui.end_row();
+
+                ui.label("Metallic:");
+                ui.add(egui::Slider::new(&mut material.metallic, 0.0..=1.0));
+                ui.end_row();
+
+                ui.label("Roughness:");
+                ui.add(egui::Slider::new(&mut material.roughness, 0.0..=1.0));
+                ui.end_row();
+
+                ui.label("Emissive:");
+                ui.color_edit_button_rgb(&mut material.emissive);
+                ui.end_row();
+
+                ui.label("Normal scale:");
+                ui.add(egui::Slider::new(&mut material.normal_scale, 0.0..=2.0));
+                ui.end_row();
+
+                ui.label("Occlusion strength:");
+                ui.add(egui::Slider::new(
+                    &mut material.occlusion_strength,
+                    0.0..=1.0,
+                ));
+                ui.end_row();
+            });
+
+        ui.add_space(5.0);
+        ui.label(egui::RichText::new("Maps").strong());
+        for map in MaterialMap::ALL {
+            let slot = material.map_mut(map);
+            ui.horizontal(|ui| match slot.texture {
+                Some(index) => {
+                    ui.checkbox(&mut slot.enabled, map.name());
+                    let (width, height) = texture_sizes.get(index).copied().unwrap_or_default();
+                    ui.weak(format!("texture {} ({}×{})", index, width, height));
+                }
+                None => {
+                    ui.add_enabled(false, egui::Checkbox::new(&mut false, map.name()));
+                    ui.weak("none");
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shader_is_valid() {
+        for shader in [PBR_SHADER, GLYPH_SHADER] {
+            let module = naga::front::wgsl::parse_str(shader).expect("parses");
+            naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::empty(),
+            )
+            .validate(&module)
+            .expect("validates");
+        }
+        // The flag constants in the shader follow the map order
+        for map in MaterialMap::ALL {
+            let name = match map {
+                MaterialMap::BaseColor => "BASE_COLOR_MAP",
+                MaterialMap::Normal => "NORMAL_MAP",
+                MaterialMap::MetallicRoughness => "METALLIC_ROUGHNESS_MAP",
+                MaterialMap::Occlusion => "OCCLUSION_MAP",
+            };
+            assert!(PBR_SHADER.contains(&format!("const {}: u32 = {}u;", name, map.flag())));
+        }
+        // And the view constants the debug view order
+        for view in DebugView::ALL {
+            let name = match view {
+                DebugView::Shaded => "SHADED_VIEW",
+                DebugView::Normals => "NORMALS_VIEW",
+                DebugView::Tangents => "TANGENTS_VIEW",
+                DebugView::Bitangents => "BITANGENTS_VIEW",
+                DebugView::MappedNormals => "MAPPED_NORMALS_VIEW",
+            };
+            let constant = format!("const {}: u32 = {}u;", name, view.shader_value());
+            assert!(PBR_SHADER.contains(&constant));
+        }
+    }
+
+    #[test]
+    fn test_uniform_layout_matches_wgsl() {
+        // Material: vec4, vec3 + f32 packed into 16 bytes, four scalars
+        assert_eq!(std::mem::size_of::<MaterialUniforms>(), 48);
+        assert_eq!(std::mem::size_of::<FrameUniforms>(), 3 * 64 + 5 * 16);
+    }
+
+    #[test]
+    fn test_material_from_gltf_fields() {
+        let imported = model_loader::Material {
+            diffuse_color: [0.5, 0.25, 1.0, 1.0],
+            metallic: 1.0,
+            roughness: 0.3,
+            base_color_texture: Some(2),
+            normal_texture: Some(0),
+            normal_scale: 0.5,
+            ..Default::default()
+        };
+        let mut material = PbrMaterial::from(&imported);
+        assert_eq!(material.base_color, [0.5, 0.25, 1.0, 1.0]);
+        assert_eq!((material.metallic, material.roughness), (1.0, 0.3));
+        assert_eq!(material.map(MaterialMap::BaseColor).active(), Some(2));
+        assert_eq!(material.map(MaterialMap::Occlusion).active(), None);
+        assert_eq!(
+            material.map_flags(),
+            MaterialMap::BaseColor.flag() | MaterialMap::Normal.flag()
+        );
+
+        material.map_mut(MaterialMap::Normal).enabled = false;
+        assert_eq!(material.uniforms().map_flags, MaterialMap::BaseColor.flag());
+        assert_eq!(material.uniforms().normal_scale, 0.5);
+    }
+
+    #[test]
+    fn test_light_direction() {
+        let lighting = Lighting {
+            light_azimuth: 90.0,
+            light_elevation: 0.0,
+            ..Default::default()
+        };
+        let [x, y, z] = lighting.light_direction();
+        assert!((x - 1.0).abs() < 1e-6 && y.abs() < 1e-6 && z.abs() < 1e-6);
+        let overhead = Lighting {
+            light_elevation: 90.0,
+            ..Default::default()
+        };
+        assert!((overhead.light_direction()[1] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_normal_matrix_undoes_non_uniform_scale() {
+        let scale = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let normals = normal_matrix(&scale);
+        assert_eq!(normals[0][0], 0.5);
+        assert_eq!(normals[1][1], 1.0);
+        assert_eq!(normal_matrix(&MAT4_IDENTITY), MAT4_IDENTITY);
+    }
+
+    #[test]
+    fn test_bounding_sphere() {
+        let model = ModelData {
+            meshes: vec![model_loader::Mesh {
+                vertices: vec![
+                    ModelVertex::new([-1.0, 0.0, 0.0], [0.0; 3], [0.0; 2]),
+                    ModelVertex::new([3.0, 0.0, 0.0], [0.0; 3], [0.0; 2]),
+                ],
+                indices: vec![0, 1, 0],
+                material_index: None,
+            }],
+            materials: Vec::new(),
+            textures: Vec::new(),
+            vertex_count: 2,
+            index_count: 3,
+        };
+        assert_eq!(bounding_sphere(&model), ([1.0, 0.0, 0.0], 2.0));
+    }
+}
@@ -76,6 +76,260 @@ pub fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
     a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
 }
 
+/// A 4x4 matrix stored as four columns, the layout WGSL expects for `mat4x4<f32>`
+pub type Mat4 = [[f32; 4]; 4];
+
+/// The identity matrix
+pub const MAT4_IDENTITY: Mat4 = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// Multiplies two column-major matrices
+///
+/// # Returns
+/// `a * b`, the transform that applies `b` first and then `a`
+///
+/// # Examples
+/// ```
+/// use wgpu_playground_core::math_utils::{mat4_mul, MAT4_IDENTITY};
+///
+/// let m = [[2.0; 4]; 4];
+/// assert_eq!(mat4_mul(&MAT4_IDENTITY, &m), m);
+/// ```
+pub fn mat4_mul(a: &Mat4, b: &Mat4) -> Mat4 {
+    let mut result = [[0.0; 4]; 4];
+    for (column, b_column) in result.iter_mut().zip(b) {
+        for (row, value) in column.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[k][row] * b_column[k]).sum();
+        }
+    }
+    result
+}
+
+/// Transforms a point, including the perspective divide
+///
+/// # Examples
+/// ```
+/// use wgpu_playground_core::math_utils::{transform_point, MAT4_IDENTITY};
+///
+/// assert_eq!(transform_point(&MAT4_IDENTITY, [1.0, 2.0, 3.0]), [1.0, 2.0, 3.0]);
+/// ```
+pub fn transform_point(m: &Mat4, p: [f32; 3]) -> [f32; 3] {
+    let [x, y, z, w] = std::array::from_fn(|row| {
+        m[0][row] * p[0] + m[1][row] * p[1] + m[2][row] * p[2] + m[3][row]
+    });
+    [x / w, y / w, z / w]
+}
+
+/// Inverts a matrix with Gauss-Jordan elimination
+///
+/// # Returns
+/// The inverse, or `None` if the matrix is singular
+///
+/// # Examples
+/// ```
+/// use wgpu_playground_core::math_utils::{mat4_inverse, rotation_y, transform_point};
+///
+/// let inverse = mat4_inverse(&rotation_y(1.0)).unwrap();
+/// let p = transform_point(&inverse, transform_point(&rotation_y(1.0), [1.0, 2.0, 3.0]));
+/// assert!((p[0] - 1.0).abs() < 1e-5);
+/// ```
+pub fn mat4_inverse(m: &Mat4) -> Option<Mat4> {
+    let mut a = *m;
+    let mut inverse = MAT4_IDENTITY;
+    // Row operations on the columns of `a`, pivoting on the largest
+    // remaining entry of each column
+    for column in 0..4 {
+        let pivot =
+            (column..4).max_by(|&i, &j| a[column][i].abs().total_cmp(&a[column][j].abs()))?;
+        if a[column][pivot].abs() < f32::EPSILON {
+            return None;
+        }
+        for matrix in [&mut a, &mut inverse] {
+            for c in matrix.iter_mut() {
+                c.swap(column, pivot);
+            }
+        }
+        let scale = 1.0 / a[column][column];
+        for matrix in [&mut a, &mut inverse] {
+            for c in matrix.iter_mut() {
+                c[column] *= scale;
+            }
+        }
+        for row in (0..4).filter(|&row| row != column) {
+            let factor = a[column][row];
+            for matrix in [&mut a, &mut inverse] {
+                for c in matrix.iter_mut() {
+                    c[row] -= factor * c[column];
+                }
+            }
+        }
+    }
+    Some(inverse)
+}
+
+/// Right-handed perspective projection into WebGPU clip space
+///
+/// # Arguments
+/// * `fov_y` - Vertical field of view in radians
+/// * `aspect` - Width divided by height
+/// * `near` - Distance of the near plane, mapped to depth 0
+/// * `far` - Distance of the far plane, mapped to depth 1
+pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+    let f = 1.0 / (fov_y / 2.0).tan();
+    [
+        [f / aspect, 0.0, 0.0, 0.0],
+        [0.0, f, 0.0, 0.0],
+        [0.0, 0.0, far / (near - far), -1.0],
+        [0.0, 0.0, near * far / (near - far), 0.0],
+    ]
+}
+
+/// Right-handed orthographic projection of a box centered on the view axis
+///
+/// # Arguments
+/// * `half_width` - Half the width of the box, mapped to x in [-1, 1]
+/// * `half_height` - Half the height of the box, mapped to y in [-1, 1]
+/// * `near` - Distance of the near plane, mapped to depth 0
+/// * `far` - Distance of the far plane, mapped to depth 1
+pub fn orthographic(half_width: f32, half_height: f32, near: f32, far: f32) -> Mat4 {
+    [
+        [1.0 / half_width, 0.0, 0.0, 0.0],
+        [0.0, 1.0 / half_height, 0.0, 0.0],
+        [0.0, 0.0, 1.0 / (near - far), 0.0],
+        [0.0, 0.0, near / (near - far), 1.0],
+    ]
+}
+
+/// Right-handed view matrix of a camera at `eye` looking at `center`
+///
+/// # Arguments
+/// * `eye` - Camera position
+/// * `center` - Point the camera looks at
+/// * `up` - Approximate up direction, must not be parallel to the view direction
+pub fn look_at(eye: [f32; 3], center: [f32; 3], up: [f32; 3]) -> Mat4 {
+    let f = normalize([center[0] - eye[0], center[1] - eye[1], center[2] - eye[2]]);
+    let s = normalize(cross(f, up));
+    let u = cross(s, f);
+
+    [
+        [s[0], u[0], -f[0], 0.0],
+        [s[1], u[1], -f[1], 0.0],
+        [s[2], u[2], -f[2], 0.0],
+        [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
+    ]
+}
+
+/// Rotation around the X axis by `angle` radians
+pub fn rotation_x(angle: f32) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, c, s, 0.0],
+        [0.0, -s, c, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Rotation around the Y axis by `angle` radians
+pub fn rotation_y(angle: f32) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    [
+        [c, 0.0, -s, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [s, 0.0, c, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Rotation around the Z axis by `angle` radians
+pub fn rotation_z(angle: f32) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    [
+        [c, s, 0.0, 0.0],
+        [-s, c, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Translation by `offset`
+///
+/// # Examples
+/// ```
+/// use wgpu_playground_core::math_utils::{transform_point, translation};
+///
+/// assert_eq!(transform_point(&translation([1.0, 2.0, 3.0]), [1.0, 1.0, 1.0]), [2.0, 3.0, 4.0]);
+/// ```
+pub fn translation(offset: [f32; 3]) -> Mat4 {
+    [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [offset[0], offset[1], offset[2], 1.0],
+    ]
+}
+
+/// Scale along each axis by `factors`
+pub fn scaling(factors: [f32; 3]) -> Mat4 {
+    [
+        [factors[0], 0.0, 0.0, 0.0],
+        [0.0, factors[1], 0.0, 0.0],
+        [0.0, 0.0, factors[2], 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Mirror across the horizontal plane `y = height`
+///
+/// Multiplied onto a view-projection matrix, it renders the scene as
+/// reflected by a planar mirror such as a water surface.
+pub fn reflection_y(height: f32) -> Mat4 {
+    [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, -1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 2.0 * height, 0.0, 1.0],
+    ]
+}
+
+/// Planes `[a, b, c, d]` bounding the view volume of a view-projection
+/// matrix, normalized and facing inwards: left, right, bottom, top, near, far
+///
+/// A point `p` is inside a plane when `a * p.x + b * p.y + c * p.z + d >= 0`.
+pub fn frustum_planes(view_proj: &Mat4) -> [[f32; 4]; 6] {
+    let row = |i: usize| {
+        [
+            view_proj[0][i],
+            view_proj[1][i],
+            view_proj[2][i],
+            view_proj[3][i],
+        ]
+    };
+    let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+    let add = |a: [f32; 4], b: [f32; 4]| std::array::from_fn(|i| a[i] + b[i]);
+    let sub = |a: [f32; 4], b: [f32; 4]| std::array::from_fn(|i| a[i] - b[i]);
+    // WebGPU clip space: -w <= x, y <= w and 0 <= z <= w
+    [add(w, x), sub(w, x), add(w, y), sub(w, y), z, sub(w, z)].map(|plane: [f32; 4]| {
+        let length = dot(
+            [plane[0], plane[1], plane[2]],
+            [plane[0], plane[1], plane[2]],
+        )
+        .sqrt();
+        plane.map(|v| v / length)
+    })
+}
+
+/// Whether a sphere is at least partly inside the planes of [`frustum_planes`]
+pub fn sphere_in_frustum(planes: &[[f32; 4]; 6], center: [f32; 3], radius: f32) -> bool {
+    planes
+        .iter()
+        .all(|p| p[0] * center[0] + p[1] * center[1] + p[2] * center[2] + p[3] >= -radius)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +385,98 @@ mod tests {
         let result = dot(a, b);
         assert_eq!(result, 0.0);
     }
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-5, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_mat4_mul_applies_right_operand_first() {
+        let m = mat4_mul(&rotation_y(std::f32::consts::FRAC_PI_2), &rotation_x(0.3));
+        let composed = transform_point(&m, [0.0, 0.0, 1.0]);
+        let stepwise = transform_point(
+            &rotation_y(std::f32::consts::FRAC_PI_2),
+            transform_point(&rotation_x(0.3), [0.0, 0.0, 1.0]),
+        );
+        assert_close(composed, stepwise);
+        assert_close(
+            transform_point(&rotation_y(std::f32::consts::FRAC_PI_2), [1.0, 0.0, 0.0]),
+            [0.0, 0.0, -1.0],
+        );
+    }
+
+    #[test]
+    fn test_perspective_maps_near_and_far_to_webgpu_depth() {
+        let projection = perspective(1.0, 1.5, 0.5, 20.0);
+        assert!(transform_point(&projection, [0.0, 0.0, -0.5])[2].abs() < 1e-6);
+        assert!((transform_point(&projection, [0.0, 0.0, -20.0])[2] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_orthographic_maps_the_box_to_webgpu_clip_space() {
+        let projection = orthographic(4.0, 2.0, 1.0, 11.0);
+        assert_close(
+            transform_point(&projection, [4.0, -2.0, -1.0]),
+            [1.0, -1.0, 0.0],
+        );
+        assert_close(
+            transform_point(&projection, [-2.0, 1.0, -6.0]),
+            [-0.5, 0.5, 0.5],
+        );
+        assert_close(
+            transform_point(&projection, [0.0, 0.0, -11.0]),
+            [0.0, 0.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn test_look_at_moves_center_in_front_of_camera() {
+        let view = look_at([3.0, 2.0, 1.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        let distance = (14.0_f32).sqrt();
+        assert_close(
+            transform_point(&view, [0.0, 0.0, 0.0]),
+            [0.0, 0.0, -distance],
+        );
+    }
+
+    #[test]
+    fn test_reflection_y_mirrors_across_the_plane() {
+        let mirror = reflection_y(1.5);
+        assert_eq!(
+            transform_point(&mirror, [2.0, 4.0, -1.0]),
+            [2.0, -1.0, -1.0]
+        );
+        assert_eq!(transform_point(&mirror, [0.5, 1.5, 3.0]), [0.5, 1.5, 3.0]);
+        assert_eq!(mat4_mul(&mirror, &mirror), MAT4_IDENTITY);
+    }
+
+    #[test]
+    fn test_mat4_inverse() {
+        let m = mat4_mul(
+            &perspective(1.0, 1.5, 0.5, 20.0),
+            &look_at([3.0, 2.0, 1.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+        );
+        let product = mat4_mul(&m, &mat4_inverse(&m).unwrap());
+        for (column, identity) in product.iter().zip(MAT4_IDENTITY) {
+            for (value, expected) in column.iter().zip(identity) {
+                assert!((value - expected).abs() < 1e-4, "{:?}", product);
+            }
+        }
+        assert_eq!(mat4_inverse(&[[1.0; 4]; 4]), None);
+    }
+
+    #[test]
+    fn test_sphere_in_frustum() {
+        let view = look_at([0.0, 0.0, 5.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        let planes = frustum_planes(&mat4_mul(&perspective(1.0, 1.0, 1.0, 10.0), &view));
+        assert!(sphere_in_frustum(&planes, [0.0, 0.0, 0.0], 0.5));
+        // Behind the camera, beyond the far plane and far off to the side
+        assert!(!sphere_in_frustum(&planes, [0.0, 0.0, 8.0], 0.5));
+        assert!(!sphere_in_frustum(&planes, [0.0, 0.0, -20.0], 0.5));
+        assert!(!sphere_in_frustum(&planes, [50.0, 0.0, 0.0], 0.5));
+        // Straddling the near plane
+        assert!(sphere_in_frustum(&planes, [0.0, 0.0, 4.5], 1.0));
+    }
 }
@@ -0,0 +1,567 @@
+//! GPU memory budget tracking
+//!
+//! Compares the memory in use against the budget the driver grants the
+//! process, so the GUI can warn before allocations start failing. On Vulkan
+//! the budget and usage of the device-local heaps come from
+//! `VK_EXT_memory_budget`, read through wgpu-hal. Other backends don't
+//! expose a budget through wgpu, but the GUI creates its instance with
+//! [`BUDGET_THRESHOLDS`], so DX12 and Vulkan turn allocations that would go
+//! over the native budget into out-of-memory errors instead of paging.
+//! Other consumers of [`crate::adapter::create_instance`] leave that to the
+//! driver.
+//!
+//! Without a driver report, usage comes from wgpu's allocator report or its
+//! internal counters, and finally from the resources the resource inspector
+//! tracks. A simulated budget replaces the native one to exercise the
+//! warnings and the out-of-memory handling on any machine.
+
+use std::fmt;
+
+/// Memory budget thresholds of the instance: creating a resource that
+/// would take a heap past 95% of its native budget fails as out of memory
+pub const BUDGET_THRESHOLDS: wgpu::MemoryBudgetThresholds = wgpu::MemoryBudgetThresholds {
+    for_resource_creation: Some(95),
+    for_device_loss: None,
+};
+
+/// Fraction of the budget above which the GUI warns, unless changed
+pub const DEFAULT_WARN_FRACTION: f32 = 0.8;
+
+/// Budget used when the simulation is turned on
+pub const DEFAULT_SIMULATED_BUDGET: u64 = 256 * MIB;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Where the usage figure of a [`MemoryReport`] comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageSource {
+    /// Heap usage reported by the driver along with the budget
+    Driver,
+    /// Memory reserved by wgpu's suballocator
+    AllocatorReport,
+    /// wgpu-hal's buffer, texture and acceleration structure memory counters
+    InternalCounters,
+    /// Sum of the resources in the resource inspector
+    Tracked,
+}
+
+impl UsageSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UsageSource::Driver => "driver heap usage",
+            UsageSource::AllocatorReport => "wgpu allocator report",
+            UsageSource::InternalCounters => "wgpu internal counters",
+            UsageSource::Tracked => "tracked resources",
+        }
+    }
+}
+
+/// Where the budget of a [`MemoryReport`] comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetSource {
+    /// Reported by the driver
+    Native,
+    /// Set in the simulated out-of-memory mode
+    Simulated,
+}
+
+impl BudgetSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BudgetSource::Native => "reported by the driver",
+            BudgetSource::Simulated => "simulated",
+        }
+    }
+}
+
+/// Usage and budget of the device-local heaps, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapBudget {
+    pub usage: u64,
+    pub budget: u64,
+}
+
+/// Memory in use against the budget, in bytes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryReport {
+    pub used: u64,
+    pub usage_source: UsageSource,
+    /// `None` when the backend reports no budget and none is simulated
+    pub budget: Option<u64>,
+    pub budget_source: Option<BudgetSource>,
+}
+
+impl MemoryReport {
+    /// Fraction of the budget in use, above 1 when over budget
+    pub fn fraction(&self) -> Option<f32> {
+        self.budget
+            .map(|budget| self.used as f32 / budget.max(1) as f32)
+    }
+}
+
+/// How close usage is to the budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BudgetLevel {
+    Normal,
+    /// Above the warning fraction of the budget
+    Warning,
+    /// At or above the budget
+    Exceeded,
+}
+
+/// Classify `used` bytes against `budget`, warning above `warn_fraction`
+pub fn budget_level(used: u64, budget: Option<u64>, warn_fraction: f32) -> BudgetLevel {
+    match budget {
+        Some(budget) if used >= budget => BudgetLevel::Exceeded,
+        Some(budget) if used as f32 / budget as f32 >= warn_fraction => BudgetLevel::Warning,
+        _ => BudgetLevel::Normal,
+    }
+}
+
+/// An allocation refused by the simulated budget
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryBudgetError {
+    OutOfMemory {
+        requested: u64,
+        used: u64,
+        budget: u64,
+    },
+}
+
+impl fmt::Display for MemoryBudgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryBudgetError::OutOfMemory {
+                requested,
+                used,
+                budget,
+            } => write!(
+                f,
+                "Out of memory: allocating {} would exceed the simulated budget ({} of {} in use)",
+                format_bytes(*requested),
+                format_bytes(*used),
+                format_bytes(*budget)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MemoryBudgetError {}
+
+/// Budget and usage of the device-local heaps, if the backend reports them
+///
+/// Only Vulkan devices with `VK_EXT_memory_budget` enabled report a budget.
+#[cfg(all(not(target_arch = "wasm32"), not(target_vendor = "apple")))]
+pub fn native_budget(device: &wgpu::Device) -> Option<HeapBudget> {
+    use ash::vk;
+
+    // SAFETY: the hal device is only used to query memory properties and
+    // is not kept beyond this call
+    let hal_device = unsafe { device.as_hal::<wgpu::hal::api::Vulkan>() }?;
+    if !hal_device
+        .enabled_device_extensions()
+        .contains(&ash::ext::memory_budget::NAME)
+    {
+        return None;
+    }
+    let instance = hal_device.shared_instance();
+    // vkGetPhysicalDeviceMemoryProperties2 is core since Vulkan 1.1
+    if instance.instance_api_version() < vk::API_VERSION_1_1 {
+        return None;
+    }
+
+    let mut budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+    let mut properties =
+        vk::PhysicalDeviceMemoryProperties2::default().push_next(&mut budget_properties);
+    // SAFETY: the physical device belongs to this instance and the output
+    // chain outlives the call
+    unsafe {
+        instance
+            .raw_instance()
+            .get_physical_device_memory_properties2(
+                hal_device.raw_physical_device(),
+                &mut properties,
+            );
+    }
+    let properties = properties.memory_properties;
+
+    let mut heaps = HeapBudget {
+        usage: 0,
+        budget: 0,
+    };
+    for (i, heap) in properties.memory_heaps_as_slice().iter().enumerate() {
+        if heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL) {
+            heaps.usage += budget_properties.heap_usage[i];
+            heaps.budget += budget_properties.heap_budget[i];
+        }
+    }
+    (heaps.budget > 0).then_some(heaps)
+}
+
+/// Budget and usage of the device-local heaps, if the backend reports them
+///
+/// Only Vulkan devices with `VK_EXT_memory_budget` enabled report a budget.
+#[cfg(any(target_arch = "wasm32", target_vendor = "apple"))]
+pub fn native_budget(_device: &wgpu::Device) -> Option<HeapBudget> {
+    None
+}
+
+/// Memory the device has allocated, if wgpu can tell
+///
+/// Prefers the allocator report (DX12 and Vulkan suballocators) over the
+/// internal counters, which stay at zero unless wgpu's `counters` feature
+/// is on and the backend maintains them.
+pub fn device_usage(device: &wgpu::Device) -> Option<(u64, UsageSource)> {
+    if let Some(report) = device.generate_allocator_report() {
+        return Some((report.total_reserved_bytes, UsageSource::AllocatorReport));
+    }
+    let hal = device.get_internal_counters().hal;
+    let bytes = hal.buffer_memory.read()
+        + hal.texture_memory.read()
+        + hal.acceleration_structure_memory.read();
+    (bytes > 0).then_some((bytes as u64, UsageSource::InternalCounters))
+}
+
+/// Memory usage against the budget, refreshed every frame
+#[derive(Debug, Clone)]
+pub struct MemoryBudget {
+    /// Fraction of the budget above which to warn
+    pub warn_fraction: f32,
+    simulated_budget: Option<u64>,
+    report: Option<MemoryReport>,
+    level: BudgetLevel,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryBudget {
+    pub fn new() -> Self {
+        Self {
+            warn_fraction: DEFAULT_WARN_FRACTION,
+            simulated_budget: None,
+            report: None,
+            level: BudgetLevel::Normal,
+        }
+    }
+
+    /// Measure the device's usage and budget
+    ///
+    /// `tracked` is the memory of the tracked resources, used when wgpu
+    /// reports no usage. Returns the new level when it rose, so the caller
+    /// warns once per crossing rather than every frame.
+    pub fn refresh(&mut self, device: &wgpu::Device, tracked: u64) -> Option<BudgetLevel> {
+        let (used, source) = device_usage(device).unwrap_or((tracked, UsageSource::Tracked));
+        self.update(used, source, native_budget(device))
+    }
+
+    /// Update from measured values; see [`Self::refresh`]
+    pub fn update(
+        &mut self,
+        used: u64,
+        usage_source: UsageSource,
+        native: Option<HeapBudget>,
+    ) -> Option<BudgetLevel> {
+        let report = match (self.simulated_budget, native) {
+            (Some(budget), _) => MemoryReport {
+                used,
+                usage_source,
+                budget: Some(budget),
+                budget_source: Some(BudgetSource::Simulated),
+            },
+            // The driver's usage covers everything the budget applies to
+            (None, Some(heaps)) => MemoryReport {
+                used: heaps.usage,
+                usage_source: UsageSource::Driver,
+                budget: Some(heaps.budget),
+                budget_source: Some(BudgetSource::Native),
+            },
+            (None, None) => MemoryReport {
+                used,
+                usage_source,
+                budget: None,
+                budget_source: None,
+            },
+        };
+        let level = budget_level(report.used, report.budget, self.warn_fraction);
+        let rose = level > self.level;
+        self.report = Some(report);
+        self.level = level;
+        rose.then_some(level)
+    }
+
+    /// The last measurement, `None` before the first refresh
+    pub fn report(&self) -> Option<&MemoryReport> {
+        self.report.as_ref()
+    }
+
+    pub fn level(&self) -> BudgetLevel {
+        self.level
+    }
+
+    pub fn simulated_budget(&self) -> Option<u64> {
+        self.simulated_budget
+    }
+
+    /// Replace the native budget with `budget` bytes, or stop simulating
+    pub fn set_simulated_budget(&mut self, budget: Option<u64>) {
+        self.simulated_budget = budget;
+    }
+
+    /// Check an allocation of `size` bytes against the simulated budget
+    ///
+    /// Only the simulated budget is enforced here; wgpu enforces the native
+    /// one itself when the instance uses [`BUDGET_THRESHOLDS`].
+    pub fn check_allocation(&self, size: u64) -> Result<(), MemoryBudgetError> {
+        let (Some(budget), Some(report)) = (self.simulated_budget, &self.report) else {
+            return Ok(());
+        };
+        if report.used.saturating_add(size) > budget {
+            return Err(MemoryBudgetError::OutOfMemory {
+                requested: size,
+                used: report.used,
+                budget,
+            });
+        }
+        Ok(())
+    }
+
+    /// Short warning for the current level, `None` below the warning fraction
+    pub fn warning(&self) -> Option<String> {
+        let report = self.report?;
+        let budget = report.budget?;
+        match self.level {
+            BudgetLevel::Normal => None,
+            BudgetLevel::Warning => Some(format!(
+                "GPU memory at {:.0}% of the budget ({} of {})",
+                report.fraction().unwrap_or(0.0) * 100.0,
+                format_bytes(report.used),
+                format_bytes(budget)
+            )),
+            BudgetLevel::Exceeded => Some(format!(
+                "GPU memory over budget ({} of {}); new allocations fail as out of memory",
+                format_bytes(report.used),
+                format_bytes(budget)
+            )),
+        }
+    }
+
+    /// Render usage against the budget and the simulation controls
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.heading("Memory Budget");
+            ui.add_space(5.0);
+
+            match self.report {
+                None => {
+                    ui.label("Not measured yet");
+                }
+                Some(report) => {
+                    match report.budget {
+                        Some(budget) => {
+                            let fraction = report.fraction().unwrap_or(0.0);
+                            let color = match self.level {
+                                BudgetLevel::Normal => egui::Color32::from_rgb(80, 160, 90),
+                                BudgetLevel::Warning => egui::Color32::from_rgb(220, 160, 40),
+                                BudgetLevel::Exceeded => egui::Color32::from_rgb(210, 60, 60),
+                            };
+                            ui.add(egui::ProgressBar::new(fraction.min(1.0)).fill(color).text(
+                                format!(
+                                    "{} of {} ({:.0}%)",
+                                    format_bytes(report.used),
+                                    format_bytes(budget),
+                                    fraction * 100.0
+                                ),
+                            ));
+                        }
+                        None => {
+                            ui.label(format!("In use: {}", format_bytes(report.used)));
+                            ui.label(
+                                egui::RichText::new(
+                                    "This backend reports no memory budget; simulate one to \
+                                     test the warnings.",
+                                )
+                                .color(egui::Color32::GRAY),
+                            );
+                        }
+                    }
+                    ui.label(
+                        egui::RichText::new(format!("Usage: {}", report.usage_source.label()))
+                            .small()
+                            .color(egui::Color32::GRAY),
+                    );
+                    if let Some(source) = report.budget_source {
+                        ui.label(
+                            egui::RichText::new(format!("Budget: {}", source.label()))
+                                .small()
+                                .color(egui::Color32::GRAY),
+                        );
+                    }
+                    if let Some(warning) = self.warning() {
+                        let color = if self.level == BudgetLevel::Exceeded {
+                            egui::Color32::RED
+                        } else {
+                            egui::Color32::YELLOW
+                        };
+                        ui.colored_label(color, format!("⚠ {}", warning));
+                    }
+                }
+            }
+
+            ui.add(
+                egui::Slider::new(&mut self.warn_fraction, 0.5..=0.99)
+                    .text("Warn at")
+                    .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)),
+            );
+
+            ui.separator();
+            let mut simulate = self.simulated_budget.is_some();
+            if ui
+                .checkbox(&mut simulate, "Simulate out-of-memory")
+                .on_hover_text(
+                    "Replace the native budget with a small one. Buffers created in the \
+                     Buffer panel beyond it fail as out of memory.",
+                )
+                .changed()
+            {
+                self.simulated_budget = simulate.then_some(DEFAULT_SIMULATED_BUDGET);
+            }
+            if let Some(budget) = &mut self.simulated_budget {
+                let mut mib = *budget / MIB;
+                if ui
+                    .add(
+                        egui::Slider::new(&mut mib, 16..=4096)
+                            .logarithmic(true)
+                            .text("Simulated budget (MiB)"),
+                    )
+                    .changed()
+                {
+                    *budget = mib * MIB;
+                }
+            }
+        });
+    }
+}
+
+/// Format bytes with a binary unit
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < MIB {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else if bytes < 1024 * MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    } else {
+        format!("{:.2} GiB", bytes as f64 / (1024 * MIB) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_level() {
+        assert_eq!(budget_level(100, None, 0.8), BudgetLevel::Normal);
+        assert_eq!(budget_level(79, Some(100), 0.8), BudgetLevel::Normal);
+        assert_eq!(budget_level(80, Some(100), 0.8), BudgetLevel::Warning);
+        assert_eq!(budget_level(100, Some(100), 0.8), BudgetLevel::Exceeded);
+        assert_eq!(budget_level(150, Some(100), 0.8), BudgetLevel::Exceeded);
+    }
+
+    #[test]
+    fn test_update_reports_rising_levels_once() {
+        let mut budget = MemoryBudget::new();
+        let heaps = |usage| {
+            Some(HeapBudget {
+                usage,
+                budget: 1000,
+            })
+        };
+        assert_eq!(budget.update(0, UsageSource::Tracked, heaps(100)), None);
+        assert_eq!(
+            budget.update(0, UsageSource::Tracked, heaps(850)),
+            Some(BudgetLevel::Warning)
+        );
+        assert_eq!(budget.update(0, UsageSource::Tracked, heaps(900)), None);
+        assert_eq!(
+            budget.update(0, UsageSource::Tracked, heaps(1000)),
+            Some(BudgetLevel::Exceeded)
+        );
+        // Falling does not warn, rising again does
+        assert_eq!(budget.update(0, UsageSource::Tracked, heaps(100)), None);
+        assert_eq!(budget.level(), BudgetLevel::Normal);
+        assert_eq!(
+            budget.update(0, UsageSource::Tracked, heaps(900)),
+            Some(BudgetLevel::Warning)
+        );
+
+        let report = budget.report().unwrap();
+        assert_eq!(report.used, 900);
+        assert_eq!(report.usage_source, UsageSource::Driver);
+        assert_eq!(report.budget_source, Some(BudgetSource::Native));
+    }
+
+    #[test]
+    fn test_no_budget_never_warns() {
+        let mut budget = MemoryBudget::new();
+        assert_eq!(budget.update(u64::MAX, UsageSource::Tracked, None), None);
+        assert_eq!(budget.level(), BudgetLevel::Normal);
+        assert_eq!(budget.report().unwrap().fraction(), None);
+        assert!(budget.warning().is_none());
+        assert!(budget.check_allocation(u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_simulated_budget_refuses_allocations() {
+        let mut budget = MemoryBudget::new();
+        budget.set_simulated_budget(Some(64 * MIB));
+        // The simulated budget replaces the native one
+        let native = Some(HeapBudget {
+            usage: 0,
+            budget: 8192 * MIB,
+        });
+        budget.update(16 * MIB, UsageSource::InternalCounters, native);
+        let report = budget.report().unwrap();
+        assert_eq!(report.budget, Some(64 * MIB));
+        assert_eq!(report.budget_source, Some(BudgetSource::Simulated));
+        assert_eq!(report.usage_source, UsageSource::InternalCounters);
+
+        assert!(budget.check_allocation(48 * MIB).is_ok());
+        let error = budget.check_allocation(48 * MIB + 1).unwrap_err();
+        assert_eq!(
+            error,
+            MemoryBudgetError::OutOfMemory {
+                requested: 48 * MIB + 1,
+                used: 16 * MIB,
+                budget: 64 * MIB,
+            }
+        );
+        assert!(error.to_string().starts_with("Out of memory"));
+
+        budget.set_simulated_budget(None);
+        assert!(budget.check_allocation(48 * MIB + 1).is_ok());
+    }
+
+    #[test]
+    fn test_warning_message() {
+        let mut budget = MemoryBudget::new();
+        budget.set_simulated_budget(Some(100 * MIB));
+        budget.update(90 * MIB, UsageSource::Tracked, None);
+        assert_eq!(
+            budget.warning().unwrap(),
+            "GPU memory at 90% of the budget (90.0 MiB of 100.0 MiB)"
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 * MIB), "3.0 MiB");
+        assert_eq!(format_bytes(2048 * MIB), "2.00 GiB");
+    }
+}
@@ -0,0 +1,749 @@
+//! Importers for PLY and binary STL meshes, and the clean-up every importer
+//! shares
+//!
+//! The importers produce the same [`ModelData`] as the OBJ and glTF loaders
+//! in [`model_loader`](crate::model_loader), so imported meshes use the
+//! [`ModelVertex::layout`] and show in the model preview like any other.
+//! Meshes without normals get smooth ones from their faces, and vertices
+//! that end up identical are welded so each is stored once.
+//!
+//! UVs are flipped to put the origin at the top left like wgpu textures;
+//! OBJ and PLY put it at the bottom left.
+
+use crate::math_utils::{cross, dot, normalize};
+use crate::model_loader::{Material, Mesh, ModelData, ModelLoadError, ModelTexture, ModelVertex};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Size of a binary STL header: 80 bytes of text and the triangle count
+const STL_HEADER_SIZE: usize = 84;
+/// Size of a binary STL triangle: normal, three corners and attribute bytes
+const STL_TRIANGLE_SIZE: usize = 50;
+
+/// Generate missing normals and weld duplicate vertices
+pub fn finish_mesh(
+    mut vertices: Vec<ModelVertex>,
+    indices: Vec<u32>,
+    has_normals: bool,
+    material_index: Option<usize>,
+) -> Mesh {
+    if !has_normals {
+        generate_normals(&mut vertices, &indices);
+    }
+    let mut mesh = Mesh {
+        vertices,
+        indices,
+        material_index,
+    };
+    deduplicate(&mut mesh);
+    mesh
+}
+
+/// Smooth normals from the triangles around each vertex
+///
+/// Faces are weighted by their area, and vertices at the same position share
+/// a normal even when they were split for their UVs. Vertices outside any
+/// triangle point up.
+pub fn generate_normals(vertices: &mut [ModelVertex], indices: &[u32]) {
+    let mut positions: HashMap<[u32; 3], usize> = HashMap::new();
+    let slots: Vec<usize> = vertices
+        .iter()
+        .map(|vertex| {
+            let next = positions.len();
+            *positions
+                .entry(vertex.position.map(f32::to_bits))
+                .or_insert(next)
+        })
+        .collect();
+
+    let mut sums = vec![[0.0f32; 3]; positions.len()];
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| triangle[i] as usize);
+        if a.max(b).max(c) >= vertices.len() {
+            continue;
+        }
+        let p0 = vertices[a].position;
+        let e1: [f32; 3] = std::array::from_fn(|i| vertices[b].position[i] - p0[i]);
+        let e2: [f32; 3] = std::array::from_fn(|i| vertices[c].position[i] - p0[i]);
+        // Twice the area in length
+        let face = cross(e1, e2);
+        for vertex in [a, b, c] {
+            let sum = &mut sums[slots[vertex]];
+            for i in 0..3 {
+                sum[i] += face[i];
+            }
+        }
+    }
+
+    for (vertex, slot) in vertices.iter_mut().zip(slots) {
+        let sum = sums[slot];
+        vertex.normal = if dot(sum, sum) > 0.0 {
+            normalize(sum)
+        } else {
+            [0.0, 1.0, 0.0]
+        };
+    }
+}
+
+/// Store each distinct vertex once and remap the indices
+///
+/// Vertices are compared bit for bit, so only exact copies are welded.
+pub fn deduplicate(mesh: &mut Mesh) {
+    let mut unique: HashMap<[u32; 8], u32> = HashMap::new();
+    let mut vertices = Vec::new();
+    let remap: Vec<u32> = mesh
+        .vertices
+        .iter()
+        .map(|&vertex| {
+            *unique
+                .entry(bytemuck::cast::<ModelVertex, [u32; 8]>(vertex))
+                .or_insert_with(|| {
+                    vertices.push(vertex);
+                    vertices.len() as u32 - 1
+                })
+        })
+        .collect();
+    for index in &mut mesh.indices {
+        if let Some(&new) = remap.get(*index as usize) {
+            *index = new;
+        }
+    }
+    mesh.vertices = vertices;
+}
+
+/// A model of one mesh without materials
+fn single_mesh_model(mesh: Mesh) -> ModelData {
+    ModelData {
+        vertex_count: mesh.vertices.len() as u32,
+        index_count: mesh.indices.len() as u32,
+        meshes: vec![mesh],
+        materials: Vec::new(),
+        textures: Vec::new(),
+    }
+}
+
+/// File name in a material's texture statement, after any options
+fn texture_file_name(statement: &str) -> Option<&str> {
+    statement.split_whitespace().last()
+}
+
+/// Load the `map_Kd` images of OBJ materials as their base color textures
+///
+/// Images shared by several materials are loaded once; missing or
+/// undecodable images are logged and leave the material untextured.
+pub fn load_diffuse_textures(materials: &mut [Material], dir: &Path) -> Vec<ModelTexture> {
+    let mut textures = Vec::new();
+    let mut loaded: HashMap<PathBuf, Option<usize>> = HashMap::new();
+    for material in materials {
+        let Some(name) = material
+            .diffuse_texture
+            .as_deref()
+            .and_then(texture_file_name)
+        else {
+            continue;
+        };
+        let index = *loaded.entry(dir.join(name)).or_insert_with_key(|path| {
+            textures.push(load_texture(path)?);
+            Some(textures.len() - 1)
+        });
+        material.base_color_texture = index;
+    }
+    textures
+}
+
+/// Decode an image file to RGBA, logging why it can't be
+fn load_texture(path: &Path) -> Option<ModelTexture> {
+    match image::open(path) {
+        Ok(image) => {
+            let image = image.to_rgba8();
+            Some(ModelTexture {
+                width: image.width(),
+                height: image.height(),
+                rgba: image.into_raw(),
+            })
+        }
+        Err(e) => {
+            log::warn!("Failed to load texture {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Images named by the `map_Kd` statements of an MTL file
+pub fn mtl_texture_paths(mtl: &Path) -> Vec<PathBuf> {
+    let dir = mtl.parent().unwrap_or_else(|| Path::new("."));
+    let Ok(text) = std::fs::read_to_string(mtl) else {
+        return Vec::new();
+    };
+    text.lines()
+        .filter_map(|line| line.trim().strip_prefix("map_Kd"))
+        .filter_map(texture_file_name)
+        .map(|name| dir.join(name))
+        .collect()
+}
+
+/// Load a binary STL file
+pub fn load_stl(path: &Path) -> Result<ModelData, ModelLoadError> {
+    parse_stl(&std::fs::read(path)?)
+}
+
+/// Parse a binary STL file
+///
+/// Each triangle gets its facet normal, or the normal of its winding when
+/// the file leaves it zero, so the mesh is flat shaded. Corners shared by
+/// triangles facing the same way are welded.
+pub fn parse_stl(data: &[u8]) -> Result<ModelData, ModelLoadError> {
+    let count = data
+        .get(80..STL_HEADER_SIZE)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+    let expected = count
+        .and_then(|count| count.checked_mul(STL_TRIANGLE_SIZE))
+        .and_then(|size| size.checked_add(STL_HEADER_SIZE));
+    let (Some(count), Some(expected)) = (count, expected) else {
+        return Err(ModelLoadError::ParseError(
+            "STL file is too short".to_string(),
+        ));
+    };
+    if data.len() < expected {
+        // Binary headers may start with "solid" too, but then the size fits
+        if data.starts_with(b"solid") {
+            return Err(ModelLoadError::UnsupportedFormat(
+                "ASCII STL; export the mesh as binary STL".to_string(),
+            ));
+        }
+        return Err(ModelLoadError::ParseError(format!(
+            "STL header promises {} triangles but the file has only {} bytes",
+            count,
+            data.len()
+        )));
+    }
+
+    let float = |offset: usize| {
+        f32::from_le_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ])
+    };
+    let vector = |offset: usize| [float(offset), float(offset + 4), float(offset + 8)];
+
+    let mut vertices = Vec::with_capacity(count * 3);
+    for triangle in 0..count {
+        let offset = STL_HEADER_SIZE + triangle * STL_TRIANGLE_SIZE;
+        let corners = [1, 2, 3].map(|corner| vector(offset + corner * 12));
+        let facet = vector(offset);
+        let normal = if dot(facet, facet) > 0.0 {
+            normalize(facet)
+        } else {
+            let e1: [f32; 3] = std::array::from_fn(|i| corners[1][i] - corners[0][i]);
+            let e2: [f32; 3] = std::array::from_fn(|i| corners[2][i] - corners[0][i]);
+            normalize(cross(e1, e2))
+        };
+        vertices.extend(
+            corners
+                .into_iter()
+                .map(|position| ModelVertex::new(position, normal, [0.0, 0.0])),
+        );
+    }
+    let indices = (0..vertices.len() as u32).collect();
+    Ok(single_mesh_model(finish_mesh(
+        vertices, indices, true, None,
+    )))
+}
+
+/// Load a PLY file in any of its ASCII and binary encodings
+pub fn load_ply(path: &Path) -> Result<ModelData, ModelLoadError> {
+    parse_ply(&std::fs::read(path)?)
+}
+
+/// Encoding of the PLY body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// Scalar type of a PLY property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyType {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => PlyType::I8,
+            "uchar" | "uint8" => PlyType::U8,
+            "short" | "int16" => PlyType::I16,
+            "ushort" | "uint16" => PlyType::U16,
+            "int" | "int32" => PlyType::I32,
+            "uint" | "uint32" => PlyType::U32,
+            "float" | "float32" => PlyType::F32,
+            "double" | "float64" => PlyType::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            PlyType::I8 | PlyType::U8 => 1,
+            PlyType::I16 | PlyType::U16 => 2,
+            PlyType::I32 | PlyType::U32 | PlyType::F32 => 4,
+            PlyType::F64 => 8,
+        }
+    }
+}
+
+/// A property of every item of a PLY element
+#[derive(Debug, Clone, PartialEq)]
+struct PlyProperty {
+    name: String,
+    /// Type of the item count for list properties
+    count: Option<PlyType>,
+    value: PlyType,
+}
+
+/// A PLY element such as `vertex` or `face`, and its properties
+#[derive(Debug, Clone, PartialEq)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+fn ply_error(message: impl Into<String>) -> ModelLoadError {
+    ModelLoadError::ParseError(format!("PLY: {}", message.into()))
+}
+
+/// Parse the header up to `end_header`, returning the encoding, the elements
+/// and the offset of the body
+fn parse_ply_header(data: &[u8]) -> Result<(PlyFormat, Vec<PlyElement>, usize), ModelLoadError> {
+    if !data.starts_with(b"ply") {
+        return Err(ply_error("missing 'ply' magic"));
+    }
+    let marker = b"end_header";
+    let end = data
+        .windows(marker.len())
+        .position(|window| window == marker)
+        .ok_or_else(|| ply_error("missing end_header"))?;
+    let body = data[end..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(data.len(), |newline| end + newline + 1);
+    let header = std::str::from_utf8(&data[..end]).map_err(|_| ply_error("header is not text"))?;
+
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    for line in header.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", "ascii", _] => format = Some(PlyFormat::Ascii),
+            ["format", "binary_little_endian", _] => format = Some(PlyFormat::BinaryLittleEndian),
+            ["format", "binary_big_endian", _] => format = Some(PlyFormat::BinaryBigEndian),
+            ["format", other, ..] => return Err(ply_error(format!("unknown format '{}'", other))),
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| ply_error(format!("bad count for element '{}'", name)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count, value, name] => {
+                let property = PlyProperty {
+                    name: name.to_string(),
+                    count: Some(
+                        PlyType::parse(count)
+                            .ok_or_else(|| ply_error(format!("unknown type '{}'", count)))?,
+                    ),
+                    value: PlyType::parse(value)
+                        .ok_or_else(|| ply_error(format!("unknown type '{}'", value)))?,
+                };
+                elements
+                    .last_mut()
+                    .ok_or_else(|| ply_error("property before any element"))?
+                    .properties
+                    .push(property);
+            }
+            ["property", value, name] => {
+                let property = PlyProperty {
+                    name: name.to_string(),
+                    count: None,
+                    value: PlyType::parse(value)
+                        .ok_or_else(|| ply_error(format!("unknown type '{}'", value)))?,
+                };
+                elements
+                    .last_mut()
+                    .ok_or_else(|| ply_error("property before any element"))?
+                    .properties
+                    .push(property);
+            }
+            _ => {} // ply, comment, obj_info
+        }
+    }
+    let format = format.ok_or_else(|| ply_error("missing format"))?;
+    Ok((format, elements, body))
+}
+
+/// Reader over the values of a PLY body
+enum PlyBody<'a> {
+    Ascii(std::str::SplitAsciiWhitespace<'a>),
+    Binary { data: &'a [u8], big_endian: bool },
+}
+
+impl PlyBody<'_> {
+    fn read(&mut self, ty: PlyType) -> Result<f64, ModelLoadError> {
+        match self {
+            PlyBody::Ascii(tokens) => tokens
+                .next()
+                .ok_or_else(|| ply_error("unexpected end of data"))?
+                .parse()
+                .map_err(|_| ply_error("bad number")),
+            PlyBody::Binary { data, big_endian } => {
+                let size = ty.size();
+                if data.len() < size {
+                    return Err(ply_error("unexpected end of data"));
+                }
+                let (bytes, rest) = data.split_at(size);
+                *data = rest;
+                // Little-endian from here on
+                let mut b = [0u8; 8];
+                b[..size].copy_from_slice(bytes);
+                if *big_endian {
+                    b[..size].reverse();
+                }
+                Ok(match ty {
+                    PlyType::I8 => b[0] as i8 as f64,
+                    PlyType::U8 => b[0] as f64,
+                    PlyType::I16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+                    PlyType::U16 => u16::from_le_bytes([b[0], b[1]]) as f64,
+                    PlyType::I32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::U32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::F64 => f64::from_le_bytes(b),
+                })
+            }
+        }
+    }
+}
+
+/// Vertex properties the importer uses, in the order of [`PLY_SLOTS`]
+const PLY_SLOTS: [&[&str]; 8] = [
+    &["x"],
+    &["y"],
+    &["z"],
+    &["nx"],
+    &["ny"],
+    &["nz"],
+    &["u", "s", "texture_u", "texture_s"],
+    &["v", "t", "texture_v", "texture_t"],
+];
+
+/// Parse a PLY file with a `vertex` and a `face` element
+///
+/// Vertices need x, y and z, and may have nx, ny, nz and UVs named u/v, s/t
+/// or texture_u/texture_v; faces are fans triangulated from
+/// `vertex_indices`. Other elements and properties are skipped.
+pub fn parse_ply(data: &[u8]) -> Result<ModelData, ModelLoadError> {
+    let (format, elements, body) = parse_ply_header(data)?;
+    let mut reader = match format {
+        PlyFormat::Ascii => PlyBody::Ascii(
+            std::str::from_utf8(&data[body..])
+                .map_err(|_| ply_error("ASCII body is not text"))?
+                .split_ascii_whitespace(),
+        ),
+        PlyFormat::BinaryLittleEndian | PlyFormat::BinaryBigEndian => PlyBody::Binary {
+            data: &data[body..],
+            big_endian: format == PlyFormat::BinaryBigEndian,
+        },
+    };
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let mut has_normals = false;
+    let mut has_faces = false;
+    for element in &elements {
+        // Slot of each property, for vertices
+        let slots: Vec<Option<usize>> = element
+            .properties
+            .iter()
+            .map(|property| {
+                PLY_SLOTS
+                    .iter()
+                    .position(|names| names.contains(&property.name.as_str()))
+            })
+            .collect();
+        let has_slot = |slot: usize| slots.contains(&Some(slot));
+        let is_vertex = element.name == "vertex";
+        if is_vertex {
+            if !(0..3).all(has_slot) {
+                return Err(ModelLoadError::MissingData(
+                    "PLY vertices without x, y and z".to_string(),
+                ));
+            }
+            has_normals = (3..6).all(has_slot);
+        }
+        let is_face = element.name == "face";
+        has_faces |= is_face;
+
+        for _ in 0..element.count {
+            let mut values = [0.0f64; 8];
+            for (property, slot) in element.properties.iter().zip(&slots) {
+                let Some(count_type) = property.count else {
+                    let value = reader.read(property.value)?;
+                    if let (true, Some(slot)) = (is_vertex, slot) {
+                        values[*slot] = value;
+                    }
+                    continue;
+                };
+                let count = reader.read(count_type)? as usize;
+                let mut corners = Vec::with_capacity(count);
+                for _ in 0..count {
+                    corners.push(reader.read(property.value)? as u32);
+                }
+                let face_indices =
+                    matches!(property.name.as_str(), "vertex_indices" | "vertex_index");
+                if is_face && face_indices {
+                    for i in 1..corners.len().saturating_sub(1) {
+                        indices.extend_from_slice(&[corners[0], corners[i], corners[i + 1]]);
+                    }
+                }
+            }
+            if is_vertex {
+                let v = values.map(|value| value as f32);
+                vertices.push(ModelVertex::new(
+                    [v[0], v[1], v[2]],
+                    [v[3], v[4], v[5]],
+                    [v[6], 1.0 - v[7]],
+                ));
+            }
+        }
+    }
+
+    if !has_faces {
+        return Err(ModelLoadError::MissingData(
+            "PLY file without faces".to_string(),
+        ));
+    }
+    if let Some(&index) = indices.iter().find(|&&i| i as usize >= vertices.len()) {
+        return Err(ply_error(format!(
+            "face refers to vertex {} of {}",
+            index,
+            vertices.len()
+        )));
+    }
+    Ok(single_mesh_model(finish_mesh(
+        vertices,
+        indices,
+        has_normals,
+        None,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASCII_QUAD: &str = "ply
+format ascii 1.0
+comment a unit quad facing +Z, split into two triangles
+element vertex 4
+property float x
+property float y
+property float z
+property float s
+property float t
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0
+1 0 0 1 0
+1 1 0 1 1
+0 1 0 0 1
+4 0 1 2 3
+";
+
+    /// Binary STL of `triangles`, each with the given facet normal
+    fn stl(triangles: &[([f32; 3], [[f32; 3]; 3])]) -> Vec<u8> {
+        let mut data = vec![0u8; 80];
+        data.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+        for (normal, corners) in triangles {
+            for value in normal.iter().chain(corners.iter().flatten()) {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            data.extend_from_slice(&[0, 0]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_ascii_ply_generates_normals() {
+        let model = parse_ply(ASCII_QUAD.as_bytes()).unwrap();
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!((model.vertex_count, model.index_count), (4, 6));
+        for vertex in &mesh.vertices {
+            assert_eq!(vertex.normal, [0.0, 0.0, 1.0]);
+        }
+        // t runs up, v runs down
+        assert_eq!(mesh.vertices[0].tex_coords, [0.0, 1.0]);
+        assert_eq!(mesh.vertices[2].tex_coords, [1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_binary_ply() {
+        for big_endian in [false, true] {
+            let mut data = format!(
+                "ply\nformat binary_{}_endian 1.0\nelement vertex 3\nproperty float x\n\
+                 property float y\nproperty float z\nproperty float nx\nproperty float ny\n\
+                 property float nz\nelement edge 1\nproperty int vertex1\nproperty int vertex2\n\
+                 element face 1\nproperty list uchar uint vertex_indices\nend_header\n",
+                if big_endian { "big" } else { "little" }
+            )
+            .into_bytes();
+            let word = |mut bytes: [u8; 4]| {
+                if big_endian {
+                    bytes.reverse();
+                }
+                bytes
+            };
+            for position in [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+                for value in position.into_iter().chain([0.0, 0.0, -1.0]) {
+                    data.extend(word(value.to_le_bytes()));
+                }
+            }
+            // The edge is skipped
+            data.extend(word(0i32.to_le_bytes()));
+            data.extend(word(1i32.to_le_bytes()));
+            data.push(3);
+            for index in [0u32, 1, 2] {
+                data.extend(word(index.to_le_bytes()));
+            }
+
+            let model = parse_ply(&data).unwrap();
+            let mesh = &model.meshes[0];
+            assert_eq!(mesh.indices, vec![0, 1, 2]);
+            assert_eq!(mesh.vertices[1].position, [1.0, 0.0, 0.0]);
+            // Normals in the file are kept, even against the winding
+            assert_eq!(mesh.vertices[2].normal, [0.0, 0.0, -1.0]);
+        }
+    }
+
+    #[test]
+    fn test_ply_errors() {
+        assert!(parse_ply(b"not a ply").is_err());
+        let out_of_range = ASCII_QUAD.replace("4 0 1 2 3", "3 0 1 9");
+        assert!(parse_ply(out_of_range.as_bytes()).is_err());
+        let truncated = &ASCII_QUAD[..ASCII_QUAD.len() - 4];
+        assert!(parse_ply(truncated.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_stl_welds_corners_and_fills_normals() {
+        let quad = [
+            (
+                [0.0, 0.0, 1.0],
+                [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+            ),
+            // No facet normal; the winding faces +Z
+            (
+                [0.0, 0.0, 0.0],
+                [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+            ),
+        ];
+        let model = parse_stl(&stl(&quad)).unwrap();
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert!(mesh.vertices.iter().all(|v| v.normal == [0.0, 0.0, 1.0]));
+
+        // A corner shared by faces pointing different ways stays split
+        let fold = [
+            quad[0],
+            (
+                [0.0, 1.0, 0.0],
+                [[0.0, 0.0, 0.0], [0.0, 0.0, -1.0], [1.0, 0.0, 0.0]],
+            ),
+        ];
+        assert_eq!(parse_stl(&stl(&fold)).unwrap().meshes[0].vertices.len(), 6);
+    }
+
+    #[test]
+    fn test_stl_errors() {
+        let mut truncated = stl(&[([0.0; 3], [[0.0; 3]; 3])]);
+        truncated.pop();
+        assert!(matches!(
+            parse_stl(&truncated),
+            Err(ModelLoadError::ParseError(_))
+        ));
+        let ascii = b"solid cube\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\n".repeat(4);
+        assert!(matches!(
+            parse_stl(&ascii),
+            Err(ModelLoadError::UnsupportedFormat(_))
+        ));
+        assert!(parse_stl(b"solid").is_err());
+    }
+
+    #[test]
+    fn test_generated_normals_are_smooth_across_uv_seams() {
+        // Two faces of a roof meeting at a ridge; the ridge is split for UVs
+        let mut vertices = vec![
+            ModelVertex::new([-1.0, 0.0, 0.0], [0.0; 3], [0.0, 0.0]),
+            ModelVertex::new([0.0, 1.0, 0.0], [0.0; 3], [1.0, 0.0]),
+            ModelVertex::new([0.0, 1.0, 1.0], [0.0; 3], [1.0, 1.0]),
+            ModelVertex::new([0.0, 1.0, 0.0], [0.0; 3], [0.0, 0.0]),
+            ModelVertex::new([1.0, 0.0, 0.0], [0.0; 3], [1.0, 0.0]),
+            ModelVertex::new([0.0, 1.0, 1.0], [0.0; 3], [0.0, 1.0]),
+        ];
+        generate_normals(&mut vertices, &[0, 2, 1, 3, 5, 4]);
+        assert_eq!(vertices[1].normal, vertices[3].normal);
+        assert!((vertices[1].normal[1] - 1.0).abs() < 1e-6);
+        assert!(vertices[0].normal[0] < 0.0 && vertices[4].normal[0] > 0.0);
+    }
+
+    #[test]
+    fn test_diffuse_textures_are_loaded_once() {
+        let dir = std::env::temp_dir().join("wgpu_playground_mesh_import_textures");
+        std::fs::create_dir_all(&dir).unwrap();
+        image::RgbaImage::from_pixel(2, 1, image::Rgba([10, 20, 30, 255]))
+            .save(dir.join("wood.png"))
+            .unwrap();
+        std::fs::write(
+            dir.join("scene.mtl"),
+            "newmtl a\nmap_Kd -bm 1 wood.png\nnewmtl b\nmap_Kd missing.png\n",
+        )
+        .unwrap();
+
+        let material = |texture: &str| Material {
+            diffuse_texture: Some(texture.to_string()),
+            ..Material::default()
+        };
+        let mut materials = vec![
+            material("-bm 1 wood.png"),
+            material("wood.png"),
+            material("missing.png"),
+            Material::default(),
+        ];
+        let textures = load_diffuse_textures(&mut materials, &dir);
+        assert_eq!(textures.len(), 1);
+        assert_eq!((textures[0].width, textures[0].height), (2, 1));
+        assert_eq!(&textures[0].rgba[..4], &[10, 20, 30, 255]);
+        let indices: Vec<_> = materials.iter().map(|m| m.base_color_texture).collect();
+        assert_eq!(indices, vec![Some(0), Some(0), None, None]);
+
+        assert_eq!(
+            mtl_texture_paths(&dir.join("scene.mtl")),
+            vec![dir.join("wood.png"), dir.join("missing.png")]
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,1025 @@
+//! Meshlet rendering with wgpu's experimental mesh shaders
+//!
+//! Mesh shaders replace the vertex stage with compute-like workgroups that
+//! write vertices and triangles straight into the rasterizer's input, and
+//! an optional task stage before them decides how many mesh workgroups to
+//! launch. That makes them a natural fit for meshlets: a model is cut into
+//! small clusters of at most [`MAX_MESHLET_VERTICES`] vertices and
+//! [`MAX_MESHLET_TRIANGLES`] triangles, the task shader culls whole
+//! clusters against the view frustum and by their normal cone, and one mesh
+//! workgroup expands each surviving cluster.
+//!
+//! The meshlets are built on the CPU by [`MeshletMesh::build`], which works
+//! everywhere. Drawing them needs [`MESH_SHADER_FEATURES`], which a device
+//! only has on Vulkan, DirectX 12 or Metal adapters that expose it and after
+//! opting in with [`request_mesh_shaders`]; [`check_support`] says what's
+//! missing on the rest.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{
+    cross, dot, frustum_planes, normalize, sphere_in_frustum, Mat4, MAT4_IDENTITY,
+};
+use crate::upload_belt;
+use wgpu::util::DeviceExt;
+
+/// Features a device needs to draw with [`MeshletRenderer`]
+pub const MESH_SHADER_FEATURES: wgpu::Features = wgpu::Features::EXPERIMENTAL_MESH_SHADER;
+
+/// Most vertices a meshlet references
+pub const MAX_MESHLET_VERTICES: usize = 64;
+
+/// Most triangles in a meshlet
+pub const MAX_MESHLET_TRIANGLES: usize = 124;
+
+/// Meshlets one task workgroup culls
+const TASK_GROUP_SIZE: u32 = 32;
+
+/// Largest cone cutoff; meshlets with it are never cone culled
+const NO_CONE_CULLING: f32 = 1.0;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub const MESH_SHADING_SHADER: &str = r#"// Mesh Shader Example
+// The task shader culls 32 meshlets per workgroup against the frustum and
+// by their normal cone and launches one mesh workgroup per survivor, which
+// writes the meshlet's vertices and triangles
+enable wgpu_mesh_shader;
+
+const TASK_GROUP_SIZE: u32 = 32u;
+const MAX_VERTICES: u32 = 64u;
+const MAX_TRIANGLES: u32 = 124u;
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    planes: array<vec4<f32>, 6>,
+    eye: vec3<f32>,
+    meshlet_count: u32,
+    frustum_culling: u32,
+    cone_culling: u32,
+    color_meshlets: u32,
+    _padding: u32,
+}
+
+struct Meshlet {
+    center: vec3<f32>,
+    radius: f32,
+    cone_axis: vec3<f32>,
+    cone_cutoff: f32,
+    vertex_offset: u32,
+    vertex_count: u32,
+    triangle_offset: u32,
+    triangle_count: u32,
+}
+
+struct Vertex {
+    position: vec3<f32>,
+    normal: vec3<f32>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var<storage, read> meshlets: array<Meshlet>;
+@group(0) @binding(2) var<storage, read> vertices: array<Vertex>;
+// Model vertex index of each meshlet vertex
+@group(0) @binding(3) var<storage, read> meshlet_vertices: array<u32>;
+// Three meshlet vertex indices per triangle, 8 bits each
+@group(0) @binding(4) var<storage, read> meshlet_triangles: array<u32>;
+
+struct TaskPayload {
+    meshlets: array<u32, TASK_GROUP_SIZE>,
+}
+
+var<task_payload> payload: TaskPayload;
+var<workgroup> visible_count: atomic<u32>;
+
+fn is_visible(meshlet: Meshlet) -> bool {
+    if uniforms.frustum_culling != 0u {
+        for (var i = 0u; i < 6u; i++) {
+            let plane = uniforms.planes[i];
+            if dot(plane.xyz, meshlet.center) + plane.w < -meshlet.radius {
+                return false;
+            }
+        }
+    }
+    if uniforms.cone_culling != 0u {
+        // Every triangle faces away when the view direction lies inside the
+        // cone of back-facing directions around the axis
+        let to_center = meshlet.center - uniforms.eye;
+        if dot(to_center, meshlet.cone_axis) >= meshlet.cone_cutoff * length(to_center) + meshlet.radius {
+            return false;
+        }
+    }
+    return true;
+}
+
+@task
+@payload(payload)
+@workgroup_size(32)
+fn ts_main(
+    @builtin(global_invocation_id) id: vec3<u32>,
+) -> @builtin(mesh_task_size) vec3<u32> {
+    let index = id.x;
+    if index < uniforms.meshlet_count && is_visible(meshlets[index]) {
+        let slot = atomicAdd(&visible_count, 1u);
+        payload.meshlets[slot] = index;
+    }
+    workgroupBarrier();
+    return vec3<u32>(atomicLoad(&visible_count), 1u, 1u);
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+}
+
+struct PrimitiveOutput {
+    @builtin(triangle_indices) indices: vec3<u32>,
+    @per_primitive @location(1) @interpolate(flat) meshlet: u32,
+}
+
+struct PrimitiveInput {
+    @per_primitive @location(1) @interpolate(flat) meshlet: u32,
+}
+
+struct MeshOutput {
+    @builtin(vertices) vertices: array<VertexOutput, MAX_VERTICES>,
+    @builtin(primitives) primitives: array<PrimitiveOutput, MAX_TRIANGLES>,
+    @builtin(vertex_count) vertex_count: u32,
+    @builtin(primitive_count) primitive_count: u32,
+}
+
+var<workgroup> mesh_output: MeshOutput;
+
+@mesh(mesh_output)
+@payload(payload)
+@workgroup_size(64)
+fn ms_main(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) group: vec3<u32>,
+) {
+    let index = payload.meshlets[group.x];
+    let meshlet = meshlets[index];
+    mesh_output.vertex_count = meshlet.vertex_count;
+    mesh_output.primitive_count = meshlet.triangle_count;
+
+    if local < meshlet.vertex_count {
+        let vertex = vertices[meshlet_vertices[meshlet.vertex_offset + local]];
+        mesh_output.vertices[local].position = uniforms.view_proj * vec4<f32>(vertex.position, 1.0);
+        mesh_output.vertices[local].normal = vertex.normal;
+    }
+    for (var triangle = local; triangle < meshlet.triangle_count; triangle += MAX_VERTICES) {
+        let packed = meshlet_triangles[meshlet.triangle_offset + triangle];
+        mesh_output.primitives[triangle].indices = vec3<u32>(packed & 0xffu, (packed >> 8u) & 0xffu, (packed >> 16u) & 0xffu);
+        mesh_output.primitives[triangle].meshlet = index;
+    }
+}
+
+fn hue(index: u32) -> vec3<f32> {
+    let h = fract(f32(index) * 0.618034);
+    return clamp(abs(fract(h + vec3<f32>(0.0, 2.0, 1.0) / 3.0) * 6.0 - 3.0) - 1.0, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@fragment
+fn fs_main(vertex: VertexOutput, primitive: PrimitiveInput) -> @location(0) vec4<f32> {
+    let normal = normalize(vertex.normal);
+    let light = max(dot(normal, normalize(vec3<f32>(0.4, 0.8, 0.45))), 0.0);
+    var color = vec3<f32>(0.8, 0.75, 0.7);
+    if uniforms.color_meshlets != 0u {
+        color = hue(primitive.meshlet) * 0.8 + 0.2;
+    }
+    return vec4<f32>(color * (0.2 + 0.8 * light), 1.0);
+}
+"#;
+
+/// Why meshlets can't be drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeshShadingError {
+    /// The device was created without [`MESH_SHADER_FEATURES`] or the mesh
+    /// shader limits
+    Unsupported {
+        adapter: String,
+        backend: wgpu::Backend,
+    },
+    /// More task workgroups than the device can launch in one draw
+    TooManyMeshlets { meshlets: usize, max: usize },
+}
+
+impl std::fmt::Display for MeshShadingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeshShadingError::Unsupported { adapter, backend } => write!(
+                f,
+                "Mesh shaders are not available on {} ({:?}): the device has no \
+                 EXPERIMENTAL_MESH_SHADER feature. They need a GPU that supports mesh \
+                 shading on Vulkan, DirectX 12 or Metal",
+                adapter, backend
+            ),
+            MeshShadingError::TooManyMeshlets { meshlets, max } => write!(
+                f,
+                "The model has {} meshlets but one draw can cull at most {}",
+                meshlets, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MeshShadingError {}
+
+/// Whether `adapter` can run task and mesh shaders
+pub fn adapter_supports_mesh_shaders(adapter: &wgpu::Adapter) -> bool {
+    adapter.features().contains(MESH_SHADER_FEATURES)
+}
+
+/// Opt `descriptor` into mesh shaders when `adapter` supports them
+///
+/// Like ray queries this is an experimental feature that needs
+/// [`wgpu::ExperimentalFeatures`], and the mesh shader limits are zero by
+/// default; they are raised to wgpu's recommended minimum, capped by what
+/// the adapter has.
+pub fn request_mesh_shaders(adapter: &wgpu::Adapter, descriptor: &mut wgpu::DeviceDescriptor) {
+    if !adapter_supports_mesh_shaders(adapter) {
+        return;
+    }
+    descriptor.required_features |= MESH_SHADER_FEATURES;
+    descriptor.required_limits = descriptor
+        .required_limits
+        .clone()
+        .using_recommended_minimum_mesh_shader_values()
+        .or_worse_values_from(&adapter.limits());
+    // SAFETY: only the mesh shader API is used experimentally, through the
+    // validated entry points of this module
+    descriptor.experimental_features = unsafe { wgpu::ExperimentalFeatures::enabled() };
+}
+
+/// Check that `device` was created with mesh shaders
+pub fn check_support(device: &wgpu::Device) -> Result<(), MeshShadingError> {
+    let limits = device.limits();
+    if device.features().contains(MESH_SHADER_FEATURES)
+        && limits.max_mesh_output_vertices as usize >= MAX_MESHLET_VERTICES
+        && limits.max_mesh_output_primitives as usize >= MAX_MESHLET_TRIANGLES
+        && limits.max_task_invocations_per_workgroup >= TASK_GROUP_SIZE
+    {
+        return Ok(());
+    }
+    let info = device.adapter_info();
+    Err(MeshShadingError::Unsupported {
+        adapter: info.name,
+        backend: info.backend,
+    })
+}
+
+/// A cluster of triangles culled as a whole
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Meshlet {
+    /// Bounding sphere of the vertices
+    pub center: [f32; 3],
+    pub radius: f32,
+    /// Average direction of the triangle normals
+    pub cone_axis: [f32; 3],
+    /// Sine of the widest angle between a triangle normal and the axis, or
+    /// 1 when the normals spread too far to ever cull the meshlet
+    pub cone_cutoff: f32,
+    /// First entry in [`MeshletMesh::meshlet_vertices`]
+    pub vertex_offset: u32,
+    pub vertex_count: u32,
+    /// First entry in [`MeshletMesh::meshlet_triangles`]
+    pub triangle_offset: u32,
+    pub triangle_count: u32,
+}
+
+impl Meshlet {
+    /// Whether every triangle faces away from a camera at `eye`, the test
+    /// the task shader runs
+    pub fn is_backfacing(&self, eye: [f32; 3]) -> bool {
+        let to_center = std::array::from_fn(|i| self.center[i] - eye[i]);
+        let distance = dot(to_center, to_center).sqrt();
+        dot(to_center, self.cone_axis) >= self.cone_cutoff * distance + self.radius
+    }
+}
+
+/// Vertex of a meshlet model
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MeshletVertex {
+    pub position: [f32; 3],
+    pub _padding0: f32,
+    pub normal: [f32; 3],
+    pub _padding1: f32,
+}
+
+impl MeshletVertex {
+    pub fn new(position: [f32; 3], normal: [f32; 3]) -> Self {
+        Self {
+            position,
+            _padding0: 0.0,
+            normal,
+            _padding1: 0.0,
+        }
+    }
+}
+
+/// A model cut into meshlets
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshletMesh {
+    pub vertices: Vec<MeshletVertex>,
+    pub meshlets: Vec<Meshlet>,
+    /// Model vertex index of every meshlet vertex, meshlet after meshlet
+    pub meshlet_vertices: Vec<u32>,
+    /// Three indices into the meshlet's vertices per triangle, packed into
+    /// the low three bytes
+    pub meshlet_triangles: Vec<u32>,
+}
+
+impl MeshletMesh {
+    /// Cut an indexed triangle list into meshlets
+    ///
+    /// Triangles are taken in order and a meshlet is closed when the next
+    /// one would go over [`MAX_MESHLET_VERTICES`] or
+    /// [`MAX_MESHLET_TRIANGLES`], so index buffers whose neighbouring
+    /// triangles are close in space give compact meshlets.
+    pub fn build(vertices: Vec<MeshletVertex>, indices: &[u32]) -> Self {
+        let mut mesh = Self {
+            vertices,
+            ..Default::default()
+        };
+        // Slot of every model vertex in the open meshlet
+        let mut slots = vec![u8::MAX; mesh.vertices.len()];
+        let mut open: Vec<u32> = Vec::new();
+        let mut triangles: Vec<[u8; 3]> = Vec::new();
+
+        for triangle in indices.chunks_exact(3) {
+            let new_vertices = triangle
+                .iter()
+                .enumerate()
+                .filter(|&(i, &v)| slots[v as usize] == u8::MAX && !triangle[..i].contains(&v))
+                .count();
+            if open.len() + new_vertices > MAX_MESHLET_VERTICES
+                || triangles.len() == MAX_MESHLET_TRIANGLES
+            {
+                mesh.close_meshlet(&mut open, &mut triangles, &mut slots);
+            }
+            triangles.push(std::array::from_fn(|corner| {
+                let vertex = triangle[corner];
+                let slot = &mut slots[vertex as usize];
+                if *slot == u8::MAX {
+                    *slot = open.len() as u8;
+                    open.push(vertex);
+                }
+                *slot
+            }));
+        }
+        mesh.close_meshlet(&mut open, &mut triangles, &mut slots);
+        mesh
+    }
+
+    fn close_meshlet(
+        &mut self,
+        open: &mut Vec<u32>,
+        triangles: &mut Vec<[u8; 3]>,
+        slots: &mut [u8],
+    ) {
+        if triangles.is_empty() {
+            return;
+        }
+        let position = |slot: u8| self.vertices[open[slot as usize] as usize].position;
+
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for &vertex in open.iter() {
+            let p = self.vertices[vertex as usize].position;
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+        }
+        let center: [f32; 3] = std::array::from_fn(|i| (min[i] + max[i]) * 0.5);
+        let radius = open
+            .iter()
+            .map(|&vertex| {
+                let p = self.vertices[vertex as usize].position;
+                let d: [f32; 3] = std::array::from_fn(|i| p[i] - center[i]);
+                dot(d, d).sqrt()
+            })
+            .fold(0.0, f32::max);
+
+        let normals: Vec<[f32; 3]> = triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let (a, b, c) = (position(a), position(b), position(c));
+                let n = cross(
+                    std::array::from_fn(|i| b[i] - a[i]),
+                    std::array::from_fn(|i| c[i] - a[i]),
+                );
+                if dot(n, n) > 0.0 {
+                    normalize(n)
+                } else {
+                    [0.0; 3]
+                }
+            })
+            .collect();
+        let sum = normals.iter().fold([0.0; 3], |sum, n| {
+            [sum[0] + n[0], sum[1] + n[1], sum[2] + n[2]]
+        });
+        let (cone_axis, cone_cutoff) = if dot(sum, sum) > 0.0 {
+            let axis = normalize(sum);
+            let min_dot = normals.iter().map(|&n| dot(n, axis)).fold(1.0f32, f32::min);
+            // Past this the back-facing cone is too narrow to be worth testing
+            if min_dot <= 0.1 {
+                (axis, NO_CONE_CULLING)
+            } else {
+                (axis, (1.0 - min_dot * min_dot).sqrt())
+            }
+        } else {
+            ([0.0, 1.0, 0.0], NO_CONE_CULLING)
+        };
+
+        self.meshlets.push(Meshlet {
+            center,
+            radius,
+            cone_axis,
+            cone_cutoff,
+            vertex_offset: self.meshlet_vertices.len() as u32,
+            vertex_count: open.len() as u32,
+            triangle_offset: self.meshlet_triangles.len() as u32,
+            triangle_count: triangles.len() as u32,
+        });
+        self.meshlet_vertices.extend_from_slice(open);
+        self.meshlet_triangles.extend(
+            triangles
+                .iter()
+                .map(|&[a, b, c]| u32::from(a) | u32::from(b) << 8 | u32::from(c) << 16),
+        );
+
+        for &vertex in open.iter() {
+            slots[vertex as usize] = u8::MAX;
+        }
+        open.clear();
+        triangles.clear();
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.meshlet_triangles.len()
+    }
+
+    /// Average triangles per meshlet
+    pub fn fill_rate(&self) -> f32 {
+        if self.meshlets.is_empty() {
+            return 0.0;
+        }
+        self.triangle_count() as f32 / (self.meshlets.len() * MAX_MESHLET_TRIANGLES) as f32
+    }
+
+    /// Meshlets whose normal cone lets them be culled from some directions
+    pub fn cone_cullable(&self) -> usize {
+        self.meshlets
+            .iter()
+            .filter(|m| m.cone_cutoff < NO_CONE_CULLING)
+            .count()
+    }
+
+    /// Meshlets that pass the task shader's culling tests for a camera,
+    /// computed on the CPU
+    pub fn count_visible(
+        &self,
+        view_proj: &Mat4,
+        eye: [f32; 3],
+        settings: &MeshletSettings,
+    ) -> usize {
+        let planes = frustum_planes(view_proj);
+        self.meshlets
+            .iter()
+            .filter(|m| !settings.frustum_culling || sphere_in_frustum(&planes, m.center, m.radius))
+            .filter(|m| !settings.cone_culling || !m.is_backfacing(eye))
+            .count()
+    }
+}
+
+/// Turns of the knot around its axis and through its hole
+const KNOT_P: f32 = 2.0;
+const KNOT_Q: f32 = 3.0;
+
+/// Rings along the knot; patches of [`PATCH_RINGS`] rings become meshlets
+const KNOT_RINGS: u32 = 784;
+
+/// Vertices around the tube
+const KNOT_SIDES: u32 = 42;
+
+/// A patch of 7x7 quads uses all 64 vertices of a meshlet and 98 triangles
+const PATCH_RINGS: u32 = 7;
+const PATCH_SIDES: u32 = 7;
+
+/// A dense (2, 3) torus knot of about 66,000 triangles, with the index
+/// buffer ordered patch by patch so every patch becomes one meshlet
+pub fn torus_knot() -> (Vec<MeshletVertex>, Vec<u32>) {
+    let curve = |t: f32| {
+        let r = 2.0 + (KNOT_Q * t).cos();
+        [
+            r * (KNOT_P * t).cos(),
+            r * (KNOT_P * t).sin(),
+            -(KNOT_Q * t).sin(),
+        ]
+        .map(|c| c * 0.8)
+    };
+    let tube_radius = 0.35;
+
+    let mut vertices = Vec::with_capacity((KNOT_RINGS * KNOT_SIDES) as usize);
+    for ring in 0..KNOT_RINGS {
+        let t = ring as f32 / KNOT_RINGS as f32 * std::f32::consts::TAU;
+        let dt = 1e-3;
+        let center = curve(t);
+        let ahead = curve(t + dt);
+        let behind = curve(t - dt);
+        let tangent = normalize(std::array::from_fn(|i| ahead[i] - behind[i]));
+        // Points towards the inside of the bend
+        let bend: [f32; 3] = std::array::from_fn(|i| ahead[i] + behind[i] - 2.0 * center[i]);
+        let binormal = normalize(cross(tangent, bend));
+        let normal = cross(binormal, tangent);
+        for side in 0..KNOT_SIDES {
+            let angle = side as f32 / KNOT_SIDES as f32 * std::f32::consts::TAU;
+            let (sin, cos) = angle.sin_cos();
+            let n: [f32; 3] = std::array::from_fn(|i| normal[i] * cos + binormal[i] * sin);
+            let position = std::array::from_fn(|i| center[i] + n[i] * tube_radius);
+            vertices.push(MeshletVertex::new(position, n));
+        }
+    }
+
+    let index = |ring: u32, side: u32| (ring % KNOT_RINGS) * KNOT_SIDES + side % KNOT_SIDES;
+    let mut indices = Vec::with_capacity((KNOT_RINGS * KNOT_SIDES * 6) as usize);
+    for patch_ring in (0..KNOT_RINGS).step_by(PATCH_RINGS as usize) {
+        for patch_side in (0..KNOT_SIDES).step_by(PATCH_SIDES as usize) {
+            for ring in patch_ring..patch_ring + PATCH_RINGS {
+                for side in patch_side..patch_side + PATCH_SIDES {
+                    let a = index(ring, side);
+                    let b = index(ring + 1, side);
+                    let c = index(ring + 1, side + 1);
+                    let d = index(ring, side + 1);
+                    indices.extend([a, d, c, c, b, a]);
+                }
+            }
+        }
+    }
+    (vertices, indices)
+}
+
+/// Culling and coloring switches of the example
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshletSettings {
+    pub frustum_culling: bool,
+    pub cone_culling: bool,
+    /// Color every meshlet differently instead of shading the model
+    pub color_meshlets: bool,
+}
+
+impl Default for MeshletSettings {
+    fn default() -> Self {
+        Self {
+            frustum_culling: true,
+            cone_culling: true,
+            color_meshlets: true,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: Mat4,
+    planes: [[f32; 4]; 6],
+    eye: [f32; 3],
+    meshlet_count: u32,
+    frustum_culling: u32,
+    cone_culling: u32,
+    color_meshlets: u32,
+    _padding: u32,
+}
+
+/// Draws a [`MeshletMesh`] with a task and a mesh shader
+pub struct MeshletRenderer {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    depth_view: wgpu::TextureView,
+    size: (u32, u32),
+    meshlet_count: u32,
+    view_proj: Mat4,
+    eye: [f32; 3],
+}
+
+impl MeshletRenderer {
+    /// Upload `mesh` and create the mesh pipeline drawing into
+    /// `color_format` targets of `width`x`height`
+    ///
+    /// Fails with [`MeshShadingError::Unsupported`] on devices without mesh
+    /// shaders.
+    pub fn new(
+        device: &wgpu::Device,
+        mesh: &MeshletMesh,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, MeshShadingError> {
+        check_support(device)?;
+        let max_groups = device.limits().max_task_mesh_workgroups_per_dimension as usize;
+        let max = max_groups * TASK_GROUP_SIZE as usize;
+        if mesh.meshlets.len() > max {
+            return Err(MeshShadingError::TooManyMeshlets {
+                meshlets: mesh.meshlets.len(),
+                max,
+            });
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Mesh Shading Shader"),
+            source: wgpu::ShaderSource::Wgsl(MESH_SHADING_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Mesh Shading Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let storage = |label: &str, contents: &[u8]| {
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents,
+                usage: wgpu::BufferUsages::STORAGE,
+            })
+        };
+        let meshlet_buffer = storage("Meshlet Buffer", bytemuck::cast_slice(&mesh.meshlets));
+        let vertex_buffer = storage(
+            "Meshlet Model Vertex Buffer",
+            bytemuck::cast_slice(&mesh.vertices),
+        );
+        let meshlet_vertex_buffer = storage(
+            "Meshlet Vertex Index Buffer",
+            bytemuck::cast_slice(&mesh.meshlet_vertices),
+        );
+        let meshlet_triangle_buffer = storage(
+            "Meshlet Triangle Buffer",
+            bytemuck::cast_slice(&mesh.meshlet_triangles),
+        );
+
+        let culling = wgpu::ShaderStages::TASK | wgpu::ShaderStages::MESH;
+        let entry = |binding, visibility, ty| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let read_only = wgpu::BufferBindingType::Storage { read_only: true };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Mesh Shading Bind Group Layout"),
+            entries: &[
+                entry(
+                    0,
+                    culling | wgpu::ShaderStages::FRAGMENT,
+                    wgpu::BufferBindingType::Uniform,
+                ),
+                entry(1, culling, read_only),
+                entry(2, wgpu::ShaderStages::MESH, read_only),
+                entry(3, wgpu::ShaderStages::MESH, read_only),
+                entry(4, wgpu::ShaderStages::MESH, read_only),
+            ],
+        });
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Mesh Shading Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: meshlet_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: vertex_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: meshlet_vertex_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: meshlet_triangle_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Mesh Shading Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+        tracker.record(ApiCategory::RenderPipeline, "create_mesh_pipeline");
+        let pipeline = device.create_mesh_pipeline(&wgpu::MeshPipelineDescriptor {
+            label: Some("Mesh Shading Pipeline"),
+            layout: Some(&pipeline_layout),
+            task: Some(wgpu::TaskState {
+                module: &shader,
+                entry_point: Some("ts_main"),
+                compilation_options: Default::default(),
+            }),
+            mesh: wgpu::MeshState {
+                module: &shader,
+                entry_point: Some("ms_main"),
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            multiview: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Ok(Self {
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            depth_view: create_depth_view(device, width, height),
+            size: (width, height),
+            meshlet_count: mesh.meshlets.len() as u32,
+            view_proj: MAT4_IDENTITY,
+            eye: [0.0; 3],
+        })
+    }
+
+    pub fn update(&mut self, view_proj: Mat4, eye: [f32; 3]) {
+        self.view_proj = view_proj;
+        self.eye = eye;
+    }
+
+    /// Task workgroups each draw launches
+    pub fn task_groups(&self) -> u32 {
+        self.meshlet_count.div_ceil(TASK_GROUP_SIZE)
+    }
+
+    /// Cull and draw the meshlets into `target` of `size` as `settings` say
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        size: (u32, u32),
+        settings: &MeshletSettings,
+    ) {
+        if size != self.size {
+            self.depth_view = create_depth_view(device, size.0, size.1);
+            self.size = size;
+        }
+        let uniforms = Uniforms {
+            view_proj: self.view_proj,
+            planes: frustum_planes(&self.view_proj),
+            eye: self.eye,
+            meshlet_count: self.meshlet_count,
+            frustum_culling: settings.frustum_culling as u32,
+            cone_culling: settings.cone_culling as u32,
+            color_meshlets: settings.color_meshlets as u32,
+            _padding: 0,
+        };
+        let tracker = ApiCoverageTracker::global();
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Mesh Shading Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Mesh Shading Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.05,
+                            g: 0.06,
+                            b: 0.08,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            tracker.record(ApiCategory::RenderPass, "set_pipeline");
+            pass.set_pipeline(&self.pipeline);
+            tracker.record(ApiCategory::RenderPass, "set_bind_group");
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "draw_mesh_tasks");
+            pass.draw_mesh_tasks(self.task_groups(), 1, 1);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+}
+
+fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Mesh Shading Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::Camera;
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(MESH_SHADING_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::MESH_SHADER,
+        )
+        .validate(&module)
+        .expect("validates");
+        assert!(MESH_SHADING_SHADER.contains(&format!(
+            "const MAX_VERTICES: u32 = {}u;",
+            MAX_MESHLET_VERTICES
+        )));
+        assert!(MESH_SHADING_SHADER.contains(&format!(
+            "const MAX_TRIANGLES: u32 = {}u;",
+            MAX_MESHLET_TRIANGLES
+        )));
+        assert!(MESH_SHADING_SHADER.contains(&format!(
+            "const TASK_GROUP_SIZE: u32 = {}u;",
+            TASK_GROUP_SIZE
+        )));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<Meshlet>(), 48);
+        assert_eq!(std::mem::size_of::<MeshletVertex>(), 32);
+        assert_eq!(std::mem::size_of::<Uniforms>(), 192);
+    }
+
+    #[test]
+    fn test_meshlets_cover_every_triangle_within_limits() {
+        let (vertices, indices) = torus_knot();
+        let mesh = MeshletMesh::build(vertices, &indices);
+        assert_eq!(mesh.triangle_count() * 3, indices.len());
+
+        let mut rebuilt = Vec::with_capacity(indices.len());
+        for meshlet in &mesh.meshlets {
+            assert!(meshlet.vertex_count as usize <= MAX_MESHLET_VERTICES);
+            assert!(meshlet.triangle_count as usize <= MAX_MESHLET_TRIANGLES);
+            let first = meshlet.vertex_offset as usize;
+            let local = &mesh.meshlet_vertices[first..first + meshlet.vertex_count as usize];
+            let first = meshlet.triangle_offset as usize;
+            for &packed in &mesh.meshlet_triangles[first..first + meshlet.triangle_count as usize] {
+                for shift in [0, 8, 16] {
+                    let slot = (packed >> shift & 0xff) as usize;
+                    assert!(slot < local.len());
+                    rebuilt.push(local[slot]);
+                }
+            }
+            // Every vertex lies in the bounding sphere
+            for &vertex in local {
+                let p = mesh.vertices[vertex as usize].position;
+                let d: [f32; 3] = std::array::from_fn(|i| p[i] - meshlet.center[i]);
+                assert!(dot(d, d).sqrt() <= meshlet.radius + 1e-4);
+            }
+        }
+        assert_eq!(rebuilt, indices);
+    }
+
+    #[test]
+    fn test_knot_patches_become_meshlets() {
+        let (vertices, indices) = torus_knot();
+        let mesh = MeshletMesh::build(vertices, &indices);
+        let patches = (KNOT_RINGS / PATCH_RINGS) * (KNOT_SIDES / PATCH_SIDES);
+        assert_eq!(mesh.meshlets.len(), patches as usize);
+        assert!(mesh
+            .meshlets
+            .iter()
+            .all(|m| m.vertex_count as usize == MAX_MESHLET_VERTICES));
+        // Each patch spans 60 degrees around the tube, narrow enough to cull
+        assert_eq!(mesh.cone_cullable(), mesh.meshlets.len());
+        // Triangles wind counter-clockwise seen from outside the tube
+        for meshlet in &mesh.meshlets {
+            let vertex = mesh.meshlet_vertices[meshlet.vertex_offset as usize];
+            assert!(dot(meshlet.cone_axis, mesh.vertices[vertex as usize].normal) > 0.5);
+        }
+    }
+
+    #[test]
+    fn test_culling_drops_hidden_meshlets() {
+        let (vertices, indices) = torus_knot();
+        let mesh = MeshletMesh::build(vertices, &indices);
+        let camera = Camera::framing([0.0; 3], 3.0);
+        let view_proj = camera.view_projection(1.0);
+        let eye = camera.eye();
+        let count = |frustum_culling, cone_culling| {
+            let settings = MeshletSettings {
+                frustum_culling,
+                cone_culling,
+                color_meshlets: false,
+            };
+            mesh.count_visible(&view_proj, eye, &settings)
+        };
+        // The framed knot is entirely in view
+        assert_eq!(count(false, false), mesh.meshlets.len());
+        assert_eq!(count(true, false), mesh.meshlets.len());
+        // Patches on the far side of the tube face entirely away
+        let front = count(false, true);
+        assert!(front < mesh.meshlets.len() * 9 / 10, "{} visible", front);
+        assert!(front > mesh.meshlets.len() / 2, "{} visible", front);
+
+        // Zoomed in on one part of the knot the frustum drops most of it
+        let close = Camera::framing([2.0, 0.0, 0.0], 0.5);
+        let settings = MeshletSettings::default();
+        let visible = mesh.count_visible(&close.view_projection(1.0), close.eye(), &settings);
+        assert!(visible < mesh.meshlets.len() / 4, "{} visible", visible);
+    }
+
+    #[test]
+    fn test_meshlets_split_at_the_triangle_limit() {
+        // A fan around vertex 0 adds one vertex per triangle
+        let vertices = (0..200)
+            .map(|i| {
+                let angle = i as f32 * 0.01;
+                MeshletVertex::new([angle.cos(), angle.sin(), 0.0], [0.0, 0.0, 1.0])
+            })
+            .collect();
+        let indices: Vec<u32> = (1..199).flat_map(|i| [0, i, i + 1]).collect();
+        let mesh = MeshletMesh::build(vertices, &indices);
+        assert_eq!(mesh.meshlets[0].vertex_count as usize, MAX_MESHLET_VERTICES);
+        assert_eq!(mesh.triangle_count(), 198);
+        // Flat triangles facing +z are culled from behind but not in front
+        let meshlet = mesh.meshlets[0];
+        assert!(meshlet.is_backfacing([0.5, 0.5, -5.0]));
+        assert!(!meshlet.is_backfacing([0.5, 0.5, 5.0]));
+    }
+
+    #[test]
+    fn test_unsupported_message() {
+        let error = MeshShadingError::Unsupported {
+            adapter: "llvmpipe".to_string(),
+            backend: wgpu::Backend::Gl,
+        };
+        assert!(error.to_string().contains("EXPERIMENTAL_MESH_SHADER"));
+    }
+}
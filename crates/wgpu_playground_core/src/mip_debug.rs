@@ -0,0 +1,876 @@
+//! Mip level visualization for sampler and texture previews
+//!
+//! [`MipDebugPreview`] draws a ground plane receding to the horizon, so one
+//! image covers everything from magnified texels near the camera to deep
+//! minification far away. It can shade the plane three ways:
+//!
+//! - [`MipDebugMode::Texture`]: the texture itself, with a full mip chain
+//! - [`MipDebugMode::MipLevel`]: a twin texture whose every level a compute
+//!   shader fills with its own color from [`MIP_COLORS`]. Sampling it with
+//!   the same sampler shows the level the hardware actually picked,
+//!   including the effect of the mipmap filter, LOD clamps and anisotropy
+//! - [`MipDebugMode::Lod`]: the level of detail estimated in the fragment
+//!   shader from UV derivatives, with the same colors and a contour at every
+//!   whole level
+//!
+//! WebGPU samplers have no LOD bias, so the preview applies its bias in the
+//! shader with `textureSampleBias`, the way a renderer would.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::upload_belt;
+
+/// Colors of mip levels 0, 1, 2, ... in linear RGB, repeating after the last
+pub const MIP_COLORS: [[f32; 3]; 12] = [
+    [0.9, 0.1, 0.1],
+    [0.95, 0.45, 0.05],
+    [0.9, 0.85, 0.1],
+    [0.35, 0.85, 0.1],
+    [0.05, 0.7, 0.45],
+    [0.05, 0.65, 0.9],
+    [0.15, 0.3, 0.95],
+    [0.5, 0.15, 0.9],
+    [0.9, 0.15, 0.7],
+    [0.6, 0.6, 0.6],
+    [0.3, 0.3, 0.3],
+    [0.95, 0.95, 0.95],
+];
+
+/// Color of mip level `level`
+pub fn mip_color(level: u32) -> [f32; 3] {
+    MIP_COLORS[level as usize % MIP_COLORS.len()]
+}
+
+/// Fills one mip level with a solid color
+pub const MIP_FILL_SHADER: &str = r#"
+@group(0) @binding(0) var level_texture: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(1) var<uniform> color: vec4<f32>;
+
+@compute @workgroup_size(8, 8)
+fn fill(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= textureDimensions(level_texture)) {
+        return;
+    }
+    textureStore(level_texture, id.xy, color);
+}
+"#;
+
+pub const MIP_DEBUG_SHADER: &str = r#"
+struct Params {
+    palette: array<vec4<f32>, 12>,
+    texture_size: vec2<f32>,
+    // Min and max LOD clamp of the sampler
+    lod_clamp: vec2<f32>,
+    viewport: vec2<f32>,
+    level_count: f32,
+    lod_bias: f32,
+    tiling: f32,
+    mode: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var image: texture_2d<f32>;
+@group(0) @binding(2) var mip_colors: texture_2d<f32>;
+@group(0) @binding(3) var image_sampler: sampler;
+
+const MODE_TEXTURE: u32 = 0u;
+const MODE_MIP_LEVEL: u32 = 1u;
+const MODE_LOD: u32 = 2u;
+// Height of the horizon in normalized device coordinates
+const HORIZON: f32 = 0.6;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+fn palette(level: u32) -> vec3<f32> {
+    return params.palette[level % 12u].rgb;
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let ndc = vec2<f32>(
+        position.x / params.viewport.x * 2.0 - 1.0,
+        1.0 - position.y / params.viewport.y * 2.0,
+    );
+    // Intersect the view ray with a ground plane one unit below the camera
+    let below = max(HORIZON - ndc.y, 0.01);
+    let distance = 1.0 / below;
+    let aspect = params.viewport.x / params.viewport.y;
+    let uv = vec2<f32>(ndc.x * aspect * distance, distance) * params.tiling * 0.25;
+
+    // Every sample and derivative is taken before branching on the mode
+    let image_color = textureSampleBias(image, image_sampler, uv, params.lod_bias);
+    let level_color = textureSampleBias(mip_colors, image_sampler, uv, params.lod_bias);
+
+    let texels = uv * params.texture_size;
+    let footprint = max(length(dpdx(texels)), length(dpdy(texels)));
+    let max_lod = min(params.lod_clamp.y, params.level_count - 1.0);
+    let lod = clamp(log2(footprint) + params.lod_bias, max(params.lod_clamp.x, 0.0), max_lod);
+    let low = u32(floor(lod));
+    let high = min(low + 1u, u32(params.level_count) - 1u);
+    var estimate = mix(palette(low), palette(high), fract(lod));
+    // Darken a thin contour where the estimate crosses a whole level
+    let to_level = abs(fract(lod + 0.5) - 0.5) / max(fwidth(lod), 1e-4);
+    estimate *= mix(0.3, 1.0, clamp(to_level, 0.0, 1.0));
+
+    var color = image_color.rgb;
+    if params.mode == MODE_MIP_LEVEL {
+        color = level_color.rgb;
+    } else if params.mode == MODE_LOD {
+        color = estimate;
+    }
+    // Fade into the distance and show sky above the horizon
+    let haze = clamp((distance - 20.0) / 30.0, 0.0, 1.0);
+    let sky = vec3<f32>(0.02, 0.02, 0.04);
+    color = mix(color, sky, haze);
+    return vec4<f32>(select(color, sky, ndc.y >= HORIZON), 1.0);
+}
+"#;
+
+/// What the preview shades the ground plane with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MipDebugMode {
+    Texture,
+    MipLevel,
+    Lod,
+}
+
+impl MipDebugMode {
+    pub const ALL: [MipDebugMode; 3] = [
+        MipDebugMode::Texture,
+        MipDebugMode::MipLevel,
+        MipDebugMode::Lod,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MipDebugMode::Texture => "Texture",
+            MipDebugMode::MipLevel => "Sampled mip level",
+            MipDebugMode::Lod => "Estimated LOD",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            MipDebugMode::Texture => "The texture through the sampler",
+            MipDebugMode::MipLevel => {
+                "The level the sampler read, from a texture with one color per level. \
+                 Blended colors show trilinear filtering between levels"
+            }
+            MipDebugMode::Lod => {
+                "log2 of the texel footprint from UV derivatives, with bias and clamps \
+                 applied. Anisotropic filtering samples sharper levels than this estimate"
+            }
+        }
+    }
+
+    fn index(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Number of levels in a full mip chain for a texture of this size
+pub fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    let c = value as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u8 {
+    let c = if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Halve an sRGB RGBA image with a 2×2 box filter in linear space
+///
+/// Odd sizes round down, repeating the last row or column.
+pub fn downsample_rgba(rgba: &[u8], width: u32, height: u32) -> (Vec<u8>, u32, u32) {
+    let (new_width, new_height) = ((width / 2).max(1), (height / 2).max(1));
+    let mut out = Vec::with_capacity((new_width * new_height * 4) as usize);
+    for y in 0..new_height {
+        for x in 0..new_width {
+            let texels = [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(dx, dy)| {
+                let sx = (x * 2 + dx).min(width - 1);
+                let sy = (y * 2 + dy).min(height - 1);
+                ((sy * width + sx) * 4) as usize
+            });
+            for channel in 0..4 {
+                let sum: f32 = texels
+                    .iter()
+                    .map(|&i| {
+                        let value = rgba[i + channel];
+                        if channel == 3 {
+                            value as f32 / 255.0
+                        } else {
+                            srgb_to_linear(value)
+                        }
+                    })
+                    .sum();
+                let average = sum / 4.0;
+                out.push(if channel == 3 {
+                    (average * 255.0).round() as u8
+                } else {
+                    linear_to_srgb(average)
+                });
+            }
+        }
+    }
+    (out, new_width, new_height)
+}
+
+/// Checkerboard with a finer grid inside each square, so every mip level
+/// has detail to lose
+pub fn procedural_texture(width: u32, height: u32) -> Vec<u8> {
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let checker = ((x / 32) + (y / 32)) % 2 == 0;
+            let line = x % 8 == 0 || y % 8 == 0;
+            let value = match (checker, line) {
+                (true, false) => 210,
+                (true, true) => 150,
+                (false, false) => 70,
+                (false, true) => 120,
+            };
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    data
+}
+
+/// Settings of the mip preview that aren't part of the sampler
+#[derive(Debug, Clone, PartialEq)]
+pub struct MipDebugSettings {
+    pub mode: MipDebugMode,
+    /// Added to the LOD by `textureSampleBias`
+    pub lod_bias: f32,
+    /// Texture repeats per unit of ground
+    pub tiling: f32,
+}
+
+impl Default for MipDebugSettings {
+    fn default() -> Self {
+        Self {
+            mode: MipDebugMode::MipLevel,
+            lod_bias: 0.0,
+            tiling: 1.0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    palette: [[f32; 4]; 12],
+    texture_size: [f32; 2],
+    lod_clamp: [f32; 2],
+    viewport: [f32; 2],
+    level_count: f32,
+    lod_bias: f32,
+    tiling: f32,
+    mode: u32,
+    _padding: [u32; 2],
+}
+
+/// The source texture and its per-level color twin
+struct MipTextures {
+    image_view: wgpu::TextureView,
+    colors_view: wgpu::TextureView,
+    size: (u32, u32),
+    level_count: u32,
+}
+
+/// Ground plane preview of how a sampler picks mip levels
+pub struct MipDebugPreview {
+    pub settings: MipDebugSettings,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    fill_pipeline: wgpu::ComputePipeline,
+    params_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    lod_clamp: (f32, f32),
+    textures: Option<MipTextures>,
+    bind_group: Option<wgpu::BindGroup>,
+    render_texture: wgpu::Texture,
+    render_view: wgpu::TextureView,
+    texture_id: Option<egui::TextureId>,
+    width: u32,
+    height: u32,
+}
+
+impl MipDebugPreview {
+    /// Create the preview with a trilinear, repeating sampler and no texture
+    pub fn new(device: &wgpu::Device) -> Self {
+        let (width, height) = (384, 256);
+        let tracker = ApiCoverageTracker::global();
+
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Mip Debug Shader"),
+            source: wgpu::ShaderSource::Wgsl(MIP_DEBUG_SHADER.into()),
+        });
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Mip Debug Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                texture_entry(1),
+                texture_entry(2),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Mip Debug Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Mip Debug Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let fill_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Mip Fill Shader"),
+            source: wgpu::ShaderSource::Wgsl(MIP_FILL_SHADER.into()),
+        });
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let fill_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Mip Fill Pipeline"),
+            layout: None,
+            module: &fill_shader,
+            entry_point: Some("fill"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Mip Debug Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Mip Debug Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::MipmapFilterMode::Linear,
+            ..Default::default()
+        });
+
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let render_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Mip Debug Render Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let render_view = render_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            settings: MipDebugSettings::default(),
+            pipeline,
+            bind_group_layout,
+            fill_pipeline,
+            params_buffer,
+            sampler,
+            lod_clamp: (0.0, 32.0),
+            textures: None,
+            bind_group: None,
+            render_texture,
+            render_view,
+            texture_id: None,
+            width,
+            height,
+        }
+    }
+
+    /// Sample with `sampler` from now on; `lod_clamp` is its min and max
+    /// LOD clamp, which the estimated LOD mode applies too
+    pub fn set_sampler(
+        &mut self,
+        device: &wgpu::Device,
+        sampler: wgpu::Sampler,
+        lod_clamp: (f32, f32),
+    ) {
+        self.sampler = sampler;
+        self.lod_clamp = lod_clamp;
+        self.update_bind_group(device);
+    }
+
+    /// Preview an sRGB RGBA image, with up to `max_levels` mip levels
+    /// generated from it
+    pub fn set_image(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        max_levels: u32,
+    ) {
+        let level_count = mip_level_count(width, height).min(max_levels.max(1));
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let tracker = ApiCoverageTracker::global();
+
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let image = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Mip Debug Image"),
+            size,
+            mip_level_count: level_count,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let mut level = (rgba.to_vec(), width, height);
+        for mip_level in 0..level_count {
+            if mip_level > 0 {
+                level = downsample_rgba(&level.0, level.1, level.2);
+            }
+            tracker.record(ApiCategory::Queue, "write_texture");
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &image,
+                    mip_level,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &level.0,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * level.1),
+                    rows_per_image: Some(level.2),
+                },
+                wgpu::Extent3d {
+                    width: level.1,
+                    height: level.2,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let colors = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Mip Debug Level Colors"),
+            size,
+            mip_level_count: level_count,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING,
+            view_formats: &[],
+        });
+        self.fill_levels(device, queue, &colors, level_count);
+
+        self.textures = Some(MipTextures {
+            image_view: image.create_view(&wgpu::TextureViewDescriptor::default()),
+            colors_view: colors.create_view(&wgpu::TextureViewDescriptor::default()),
+            size: (width, height),
+            level_count,
+        });
+        self.update_bind_group(device);
+    }
+
+    /// Fill every level of `colors` with its color from [`MIP_COLORS`]
+    fn fill_levels(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        colors: &wgpu::Texture,
+        level_count: u32,
+    ) {
+        use wgpu::util::DeviceExt;
+
+        let tracker = ApiCoverageTracker::global();
+        let layout = self.fill_pipeline.get_bind_group_layout(0);
+        let bind_groups: Vec<(wgpu::BindGroup, u32, u32)> = (0..level_count)
+            .map(|level| {
+                let [r, g, b] = mip_color(level);
+                tracker.record(ApiCategory::Buffer, "create_buffer");
+                let color = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Mip Fill Color Buffer"),
+                    contents: bytemuck::cast_slice(&[r, g, b, 1.0]),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+                let view = colors.create_view(&wgpu::TextureViewDescriptor {
+                    base_mip_level: level,
+                    mip_level_count: Some(1),
+                    ..Default::default()
+                });
+                tracker.record(ApiCategory::BindGroup, "create_bind_group");
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Mip Fill Bind Group"),
+                    layout: &layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: color.as_entire_binding(),
+                        },
+                    ],
+                });
+                let width = (colors.width() >> level).max(1);
+                let height = (colors.height() >> level).max(1);
+                (bind_group, width, height)
+            })
+            .collect();
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Mip Fill Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Mip Fill Pass"),
+                timestamp_writes: None,
+            });
+            tracker.record(ApiCategory::ComputePass, "set_pipeline");
+            pass.set_pipeline(&self.fill_pipeline);
+            for (bind_group, width, height) in &bind_groups {
+                tracker.record(ApiCategory::ComputePass, "set_bind_group");
+                pass.set_bind_group(0, bind_group, &[]);
+                tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+                pass.dispatch_workgroups(width.div_ceil(8), height.div_ceil(8), 1);
+            }
+        }
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(Some(encoder.finish()));
+    }
+
+    fn update_bind_group(&mut self, device: &wgpu::Device) {
+        let Some(textures) = &self.textures else {
+            return;
+        };
+        ApiCoverageTracker::global().record(ApiCategory::BindGroup, "create_bind_group");
+        self.bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Mip Debug Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&textures.image_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&textures.colors_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        }));
+    }
+
+    /// Draw the ground plane with the current settings
+    pub fn render(&self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let (Some(textures), Some(bind_group)) = (&self.textures, &self.bind_group) else {
+            return;
+        };
+        let tracker = ApiCoverageTracker::global();
+
+        let params = Params {
+            palette: std::array::from_fn(|i| {
+                let [r, g, b] = MIP_COLORS[i];
+                [r, g, b, 1.0]
+            }),
+            texture_size: [textures.size.0 as f32, textures.size.1 as f32],
+            lod_clamp: [self.lod_clamp.0, self.lod_clamp.1],
+            viewport: [self.width as f32, self.height as f32],
+            level_count: textures.level_count as f32,
+            lod_bias: self.settings.lod_bias,
+            tiling: self.settings.tiling,
+            mode: self.settings.mode.index(),
+            _padding: [0; 2],
+        };
+        upload_belt::write_buffer(queue, &self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Mip Debug Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Mip Debug Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.render_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            tracker.record(ApiCategory::RenderPass, "set_pipeline");
+            pass.set_pipeline(&self.pipeline);
+            tracker.record(ApiCategory::RenderPass, "set_bind_group");
+            pass.set_bind_group(0, bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "draw");
+            pass.draw(0..3, 0..1);
+        }
+        upload_belt::submit(queue, Some(encoder.finish()));
+    }
+
+    /// Whether an image has been set
+    pub fn has_texture(&self) -> bool {
+        self.textures.is_some()
+    }
+
+    /// Number of mip levels of the previewed texture
+    pub fn level_count(&self) -> u32 {
+        self.textures.as_ref().map_or(0, |t| t.level_count)
+    }
+
+    /// The texture the preview renders into
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.render_texture
+    }
+
+    /// Get preview canvas size
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Get or register texture ID for egui
+    ///
+    /// Note: This method is only available when building for native targets.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_texture_id(
+        &mut self,
+        device: &wgpu::Device,
+        renderer: &mut egui_wgpu::Renderer,
+    ) -> egui::TextureId {
+        *self.texture_id.get_or_insert_with(|| {
+            renderer.register_native_texture(
+                device,
+                &self.render_view,
+                egui_wgpu::wgpu::FilterMode::Linear,
+            )
+        })
+    }
+
+    /// Mode, bias and tiling controls, the rendered plane and a legend of
+    /// the level colors
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        ui.horizontal(|ui| {
+            for mode in MipDebugMode::ALL {
+                ui.selectable_value(&mut self.settings.mode, mode, mode.name())
+                    .on_hover_text(mode.description());
+            }
+        });
+        ui.add(egui::Slider::new(&mut self.settings.lod_bias, -4.0..=4.0).text("LOD bias"))
+            .on_hover_text(
+                "Applied with textureSampleBias; WebGPU samplers have no bias of their own",
+            );
+        ui.add(
+            egui::Slider::new(&mut self.settings.tiling, 0.25..=16.0)
+                .logarithmic(true)
+                .text("Tiling"),
+        );
+
+        self.render(device, queue);
+        let texture_id = self.get_texture_id(device, renderer);
+        let (width, height) = self.size();
+        ui.add(egui::Image::new(egui::load::SizedTexture::new(
+            texture_id,
+            egui::vec2(width as f32, height as f32),
+        )));
+
+        if let Some(textures) = &self.textures {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Levels:");
+                for level in 0..textures.level_count {
+                    let [r, g, b] = mip_color(level);
+                    let (rect, response) =
+                        ui.allocate_exact_size(egui::vec2(18.0, 18.0), egui::Sense::hover());
+                    ui.painter()
+                        .rect_filled(rect, 2.0, egui::Rgba::from_rgb(r, g, b));
+                    ui.painter().text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        level.to_string(),
+                        egui::FontId::monospace(10.0),
+                        egui::Color32::BLACK,
+                    );
+                    let (w, h) = textures.size;
+                    response.on_hover_text(format!(
+                        "Level {}: {}×{}",
+                        level,
+                        (w >> level).max(1),
+                        (h >> level).max(1)
+                    ));
+                }
+            });
+        }
+        ui.weak(self.settings.mode.description());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        validate(MIP_DEBUG_SHADER);
+        validate(MIP_FILL_SHADER);
+    }
+
+    #[test]
+    fn test_params_match_wgsl_layout() {
+        // 12 palette entries, three vec2s, four scalars, rounded up to 16
+        assert_eq!(std::mem::size_of::<Params>(), 12 * 16 + 3 * 8 + 4 * 4 + 8);
+        assert!(MIP_DEBUG_SHADER.contains(&format!("array<vec4<f32>, {}>", MIP_COLORS.len())));
+        for mode in MipDebugMode::ALL {
+            let name = match mode {
+                MipDebugMode::Texture => "MODE_TEXTURE",
+                MipDebugMode::MipLevel => "MODE_MIP_LEVEL",
+                MipDebugMode::Lod => "MODE_LOD",
+            };
+            assert!(MIP_DEBUG_SHADER.contains(&format!(
+                "const {}: u32 = {}u;",
+                name,
+                mode.index()
+            )));
+        }
+    }
+
+    #[test]
+    fn test_mip_level_count() {
+        assert_eq!(mip_level_count(1, 1), 1);
+        assert_eq!(mip_level_count(256, 256), 9);
+        assert_eq!(mip_level_count(300, 20), 9);
+        assert_eq!(mip_level_count(0, 0), 1);
+    }
+
+    #[test]
+    fn test_mip_colors_repeat() {
+        assert_eq!(mip_color(0), MIP_COLORS[0]);
+        assert_eq!(mip_color(MIP_COLORS.len() as u32 + 2), MIP_COLORS[2]);
+    }
+
+    #[test]
+    fn test_downsample_averages_in_linear_space() {
+        let black_and_white = [0, 0, 0, 255, 255, 255, 255, 255];
+        let (pixels, width, height) = downsample_rgba(&black_and_white, 2, 1);
+        assert_eq!((width, height), (1, 1));
+        // Half the light is sRGB 188, not the naive 128
+        assert_eq!(pixels, vec![188, 188, 188, 255]);
+
+        let (pixels, width, height) = downsample_rgba(&procedural_texture(64, 64), 64, 64);
+        assert_eq!((width, height), (32, 32));
+        assert_eq!(pixels.len(), 32 * 32 * 4);
+    }
+}
@@ -1,4 +1,7 @@
-use std::path::Path;
+use crate::asset_cache::{AssetCache, CacheKey};
+use crate::mesh_import;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use wgpu::util::DeviceExt;
 use wgpu::{Buffer, Device};
 
@@ -47,6 +50,10 @@ unsafe impl bytemuck::Pod for ModelVertex {}
 unsafe impl bytemuck::Zeroable for ModelVertex {}
 
 impl ModelVertex {
+    /// Position, normal and UV at shader locations 0, 1 and 2
+    pub const ATTRIBUTES: [wgpu::VertexAttribute; 3] =
+        wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x2];
+
     pub fn new(position: [f32; 3], normal: [f32; 3], tex_coords: [f32; 2]) -> Self {
         Self {
             position,
@@ -54,16 +61,40 @@ impl ModelVertex {
             tex_coords,
         }
     }
+
+    /// Vertex buffer layout of the meshes every importer produces
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
 }
 
 /// Material information from the model
-#[derive(Debug, Clone)]
+///
+/// The metallic-roughness fields follow glTF 2.0; texture fields index
+/// [`ModelData::textures`]. OBJ materials get a roughness derived from
+/// their shininess and no metal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Material {
     pub name: String,
     pub diffuse_color: [f32; 4],
     pub diffuse_texture: Option<String>,
     pub specular_color: Option<[f32; 3]>,
     pub shininess: Option<f32>,
+    pub metallic: f32,
+    pub roughness: f32,
+    pub emissive: [f32; 3],
+    pub base_color_texture: Option<usize>,
+    /// Roughness in the green channel, metalness in blue
+    pub metallic_roughness_texture: Option<usize>,
+    pub normal_texture: Option<usize>,
+    pub normal_scale: f32,
+    pub occlusion_texture: Option<usize>,
+    pub occlusion_strength: f32,
 }
 
 impl Default for Material {
@@ -74,6 +105,43 @@ impl Default for Material {
             diffuse_texture: None,
             specular_color: None,
             shininess: None,
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive: [0.0; 3],
+            base_color_texture: None,
+            metallic_roughness_texture: None,
+            normal_texture: None,
+            normal_scale: 1.0,
+            occlusion_texture: None,
+            occlusion_strength: 1.0,
+        }
+    }
+}
+
+/// Roughness matching a Phong specular exponent
+///
+/// Uses the usual Blinn-Phong to GGX conversion
+/// `alpha = sqrt(2 / (shininess + 2))`, with `roughness = sqrt(alpha)`.
+pub fn roughness_from_shininess(shininess: f32) -> f32 {
+    (2.0 / (shininess.max(0.0) + 2.0)).sqrt().sqrt()
+}
+
+/// An image used by the model's materials, as 8-bit RGBA
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelTexture {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major, 4 bytes per pixel
+    pub rgba: Vec<u8>,
+}
+
+impl ModelTexture {
+    /// A single white pixel, standing in for images that can't be decoded
+    pub fn white() -> Self {
+        Self {
+            width: 1,
+            height: 1,
+            rgba: vec![255; 4],
         }
     }
 }
@@ -90,6 +158,8 @@ pub struct Mesh {
 pub struct ModelData {
     pub meshes: Vec<Mesh>,
     pub materials: Vec<Material>,
+    /// Images referenced by the materials' texture indices
+    pub textures: Vec<ModelTexture>,
     pub vertex_count: u32,
     pub index_count: u32,
 }
@@ -148,6 +218,8 @@ pub fn load_model_from_file(path: &Path) -> Result<ModelData, ModelLoadError> {
     match extension.to_lowercase().as_str() {
         "obj" => load_obj(path),
         "gltf" | "glb" => load_gltf(path),
+        "ply" => mesh_import::load_ply(path),
+        "stl" => mesh_import::load_stl(path),
         _ => Err(ModelLoadError::UnsupportedFormat(format!(
             "Unsupported file extension: {}",
             extension
@@ -155,6 +227,182 @@ pub fn load_model_from_file(path: &Path) -> Result<ModelData, ModelLoadError> {
     }
 }
 
+/// Version of the cached model encoding, part of the cache key
+const MODEL_CACHE_VERSION: u32 = 3;
+
+/// Load a 3D model, reusing previously processed geometry from `cache`
+///
+/// The cache key covers the model file and the files it references
+/// (`mtllib` materials and their diffuse textures for OBJ, external buffers
+/// for glTF), so editing any of them triggers a fresh import.
+pub fn load_model_cached(path: &Path, cache: &AssetCache) -> Result<ModelData, ModelLoadError> {
+    let mut key = CacheKey::builder("mesh").param("version", MODEL_CACHE_VERSION);
+    key = key.content(&std::fs::read(path)?);
+    for dependency in model_dependencies(path) {
+        key = key
+            .param("dependency", dependency.display())
+            .content(&std::fs::read(&dependency).unwrap_or_default());
+    }
+    let key = key.build();
+
+    if let Some(model) = cache.get(&key).and_then(|data| decode_model(&data)) {
+        log::info!("Loaded {} from asset cache", path.display());
+        return Ok(model);
+    }
+
+    let model = load_model_from_file(path)?;
+    if let Err(e) = cache.put(&key, &encode_model(&model)) {
+        log::warn!("Failed to cache model {}: {}", path.display(), e);
+    }
+    Ok(model)
+}
+
+/// Files referenced by a model that affect its processed geometry
+fn model_dependencies(path: &Path) -> Vec<PathBuf> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    let Ok(text) = std::fs::read_to_string(path) else {
+        // Binary formats (GLB) embed their buffers
+        return Vec::new();
+    };
+
+    match extension.as_deref() {
+        Some("obj") => {
+            let libraries: Vec<PathBuf> = text
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("mtllib"))
+                .flat_map(str::split_whitespace)
+                .map(|name| dir.join(name))
+                .collect();
+            let textures: Vec<PathBuf> = libraries
+                .iter()
+                .flat_map(|library| mesh_import::mtl_texture_paths(library))
+                .collect();
+            libraries.into_iter().chain(textures).collect()
+        }
+        Some("gltf") => serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|json| json.get("buffers").and_then(|b| b.as_array()).cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|buffer| buffer.get("uri").and_then(|uri| uri.as_str()))
+            .filter(|uri| !uri.starts_with("data:"))
+            .map(|uri| dir.join(uri))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Serialize model data for the asset cache
+///
+/// Layout: material JSON length and bytes, mesh count, then for each mesh
+/// its material index (`u32::MAX` for none), vertex and index counts
+/// followed by the raw vertex and index data, then the texture count and
+/// for each texture its width, height and RGBA bytes. All integers are
+/// little-endian.
+fn encode_model(model: &ModelData) -> Vec<u8> {
+    let materials = serde_json::to_vec(&model.materials).unwrap_or_default();
+    let mut data = Vec::new();
+    data.extend_from_slice(&(materials.len() as u32).to_le_bytes());
+    data.extend_from_slice(&materials);
+    data.extend_from_slice(&(model.meshes.len() as u32).to_le_bytes());
+    for mesh in &model.meshes {
+        let material = mesh.material_index.map_or(u32::MAX, |i| i as u32);
+        data.extend_from_slice(&material.to_le_bytes());
+        data.extend_from_slice(&(mesh.vertices.len() as u32).to_le_bytes());
+        data.extend_from_slice(&(mesh.indices.len() as u32).to_le_bytes());
+        data.extend_from_slice(bytemuck::cast_slice(&mesh.vertices));
+        data.extend_from_slice(bytemuck::cast_slice(&mesh.indices));
+    }
+    data.extend_from_slice(&(model.textures.len() as u32).to_le_bytes());
+    for texture in &model.textures {
+        data.extend_from_slice(&texture.width.to_le_bytes());
+        data.extend_from_slice(&texture.height.to_le_bytes());
+        data.extend_from_slice(&texture.rgba);
+    }
+    data
+}
+
+/// Sequential reader over encoded model data
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn pods<T: bytemuck::Pod>(&mut self, count: usize) -> Option<Vec<T>> {
+        let size = std::mem::size_of::<T>();
+        let bytes = self.take(count.checked_mul(size)?)?;
+        Some(
+            bytes
+                .chunks_exact(size)
+                .map(bytemuck::pod_read_unaligned)
+                .collect(),
+        )
+    }
+}
+
+/// Inverse of [`encode_model`], `None` if the data is malformed
+fn decode_model(data: &[u8]) -> Option<ModelData> {
+    let mut reader = ByteReader(data);
+    let materials_len = reader.u32()? as usize;
+    let materials: Vec<Material> = serde_json::from_slice(reader.take(materials_len)?).ok()?;
+    let mesh_count = reader.u32()?;
+
+    let mut meshes = Vec::new();
+    let mut vertex_count = 0;
+    let mut index_count = 0;
+    for _ in 0..mesh_count {
+        let material = reader.u32()?;
+        let vertices = reader.u32()?;
+        let indices = reader.u32()?;
+        vertex_count += vertices;
+        index_count += indices;
+        meshes.push(Mesh {
+            vertices: reader.pods(vertices as usize)?,
+            indices: reader.pods(indices as usize)?,
+            material_index: (material != u32::MAX).then_some(material as usize),
+        });
+    }
+
+    let texture_count = reader.u32()?;
+    let mut textures = Vec::new();
+    for _ in 0..texture_count {
+        let width = reader.u32()?;
+        let height = reader.u32()?;
+        let len = (width as usize)
+            .checked_mul(height as usize)?
+            .checked_mul(4)?;
+        textures.push(ModelTexture {
+            width,
+            height,
+            rgba: reader.take(len)?.to_vec(),
+        });
+    }
+
+    Some(ModelData {
+        meshes,
+        materials,
+        textures,
+        vertex_count,
+        index_count,
+    })
+}
+
 /// Load an OBJ model
 fn load_obj(path: &Path) -> Result<ModelData, ModelLoadError> {
     let (models, materials) = tobj::load_obj(
@@ -184,10 +432,18 @@ fn load_obj(path: &Path) -> Result<ModelData, ModelLoadError> {
                 diffuse_texture: mat.diffuse_texture,
                 specular_color: mat.specular,
                 shininess: mat.shininess,
+                roughness: mat
+                    .shininess
+                    .map_or(Material::default().roughness, roughness_from_shininess),
+                ..Material::default()
             });
         }
     }
 
+    // Diffuse maps are resolved next to the OBJ, where tobj found the MTL
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let textures = mesh_import::load_diffuse_textures(&mut model_materials, dir);
+
     let mut total_vertices = 0;
     let mut total_indices = 0;
 
@@ -210,11 +466,12 @@ fn load_obj(path: &Path) -> Result<ModelData, ModelLoadError> {
                     mesh.normals[i * 3 + 2],
                 ]
             } else {
-                [0.0, 1.0, 0.0] // Default normal
+                [0.0, 1.0, 0.0] // Replaced by generated normals
             };
 
+            // OBJ puts the UV origin at the bottom left, wgpu at the top left
             let tex_coords = if !mesh.texcoords.is_empty() {
-                [mesh.texcoords[i * 2], mesh.texcoords[i * 2 + 1]]
+                [mesh.texcoords[i * 2], 1.0 - mesh.texcoords[i * 2 + 1]]
             } else {
                 [0.0, 0.0] // Default UVs
             };
@@ -222,19 +479,21 @@ fn load_obj(path: &Path) -> Result<ModelData, ModelLoadError> {
             vertices.push(ModelVertex::new(position, normal, tex_coords));
         }
 
-        total_vertices += vertices.len() as u32;
-        total_indices += mesh.indices.len() as u32;
-
-        meshes.push(Mesh {
+        let mesh = mesh_import::finish_mesh(
             vertices,
-            indices: mesh.indices.clone(),
-            material_index: mesh.material_id,
-        });
+            mesh.indices.clone(),
+            !mesh.normals.is_empty(),
+            mesh.material_id,
+        );
+        total_vertices += mesh.vertices.len() as u32;
+        total_indices += mesh.indices.len() as u32;
+        meshes.push(mesh);
     }
 
     Ok(ModelData {
         meshes,
         materials: model_materials,
+        textures,
         vertex_count: total_vertices,
         index_count: total_indices,
     })
@@ -242,7 +501,7 @@ fn load_obj(path: &Path) -> Result<ModelData, ModelLoadError> {
 
 /// Load a glTF/GLB model
 fn load_gltf(path: &Path) -> Result<ModelData, ModelLoadError> {
-    let (document, buffers, _images) = gltf::import(path)
+    let (document, buffers, images) = gltf::import(path)
         .map_err(|e| ModelLoadError::ParseError(format!("Failed to load glTF: {}", e)))?;
 
     let mut meshes = Vec::new();
@@ -252,6 +511,8 @@ fn load_gltf(path: &Path) -> Result<ModelData, ModelLoadError> {
     for material in document.materials() {
         let pbr = material.pbr_metallic_roughness();
         let base_color = pbr.base_color_factor();
+        let normal = material.normal_texture();
+        let occlusion = material.occlusion_texture();
 
         materials.push(Material {
             name: "material".to_string(), // gltf 1.4 doesn't expose name directly
@@ -261,9 +522,39 @@ fn load_gltf(path: &Path) -> Result<ModelData, ModelLoadError> {
                 .map(|tex| format!("texture_{}", tex.texture().index())),
             specular_color: None,
             shininess: None,
+            metallic: pbr.metallic_factor(),
+            roughness: pbr.roughness_factor(),
+            emissive: material.emissive_factor(),
+            base_color_texture: pbr
+                .base_color_texture()
+                .map(|info| info.texture().source().index()),
+            metallic_roughness_texture: pbr
+                .metallic_roughness_texture()
+                .map(|info| info.texture().source().index()),
+            normal_texture: normal
+                .as_ref()
+                .map(|normal| normal.texture().source().index()),
+            normal_scale: normal.as_ref().map_or(1.0, |normal| normal.scale()),
+            occlusion_texture: occlusion
+                .as_ref()
+                .map(|occlusion| occlusion.texture().source().index()),
+            occlusion_strength: occlusion
+                .as_ref()
+                .map_or(1.0, |occlusion| occlusion.strength()),
         });
     }
 
+    // Keep one texture per image so material indices stay valid
+    let textures = images
+        .iter()
+        .map(|image| {
+            gltf_image_to_rgba(image).unwrap_or_else(|| {
+                log::warn!("Unsupported glTF image format {:?}", image.format);
+                ModelTexture::white()
+            })
+        })
+        .collect();
+
     let mut total_vertices = 0;
     let mut total_indices = 0;
 
@@ -319,11 +610,59 @@ fn load_gltf(path: &Path) -> Result<ModelData, ModelLoadError> {
     Ok(ModelData {
         meshes,
         materials,
+        textures,
         vertex_count: total_vertices,
         index_count: total_indices,
     })
 }
 
+/// Convert a decoded glTF image to 8-bit RGBA, keeping the high byte of
+/// 16-bit channels; `None` for floating-point images
+fn gltf_image_to_rgba(image: &gltf::image::Data) -> Option<ModelTexture> {
+    use gltf::image::Format;
+    let (channels, bytes_per_channel) = match image.format {
+        Format::R8 => (1, 1),
+        Format::R8G8 => (2, 1),
+        Format::R8G8B8 => (3, 1),
+        Format::R8G8B8A8 => (4, 1),
+        Format::R16 => (1, 2),
+        Format::R16G16 => (2, 2),
+        Format::R16G16B16 => (3, 2),
+        Format::R16G16B16A16 => (4, 2),
+        _ => return None,
+    };
+    Some(ModelTexture {
+        width: image.width,
+        height: image.height,
+        rgba: expand_to_rgba(&image.pixels, channels, bytes_per_channel),
+    })
+}
+
+/// Expand pixels of 1 to 4 little-endian channels to 8-bit RGBA
+///
+/// Gray images are replicated to RGB; missing channels are 0 except alpha,
+/// which is opaque.
+fn expand_to_rgba(pixels: &[u8], channels: usize, bytes_per_channel: usize) -> Vec<u8> {
+    let channel = |pixel: &[u8], c: usize| pixel[c * bytes_per_channel + bytes_per_channel - 1];
+    pixels
+        .chunks_exact(channels * bytes_per_channel)
+        .flat_map(|pixel| match channels {
+            1 => {
+                let gray = channel(pixel, 0);
+                [gray, gray, gray, 255]
+            }
+            2 => [channel(pixel, 0), channel(pixel, 1), 0, 255],
+            3 => [channel(pixel, 0), channel(pixel, 1), channel(pixel, 2), 255],
+            _ => [
+                channel(pixel, 0),
+                channel(pixel, 1),
+                channel(pixel, 2),
+                channel(pixel, 3),
+            ],
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,4 +678,83 @@ mod tests {
         assert_eq!(material.name, "default");
         assert_eq!(material.diffuse_color, [1.0, 1.0, 1.0, 1.0]);
     }
+
+    #[test]
+    fn test_model_cache_encoding_round_trip() {
+        let model = ModelData {
+            meshes: vec![
+                Mesh {
+                    vertices: vec![
+                        ModelVertex::new([0.0, 1.0, 2.0], [0.0, 0.0, 1.0], [0.5, 0.5]),
+                        ModelVertex::new([3.0, 4.0, 5.0], [0.0, 1.0, 0.0], [1.0, 0.0]),
+                    ],
+                    indices: vec![0, 1, 0],
+                    material_index: Some(0),
+                },
+                Mesh {
+                    vertices: Vec::new(),
+                    indices: Vec::new(),
+                    material_index: None,
+                },
+            ],
+            materials: vec![Material::default()],
+            textures: vec![ModelTexture {
+                width: 2,
+                height: 1,
+                rgba: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            }],
+            vertex_count: 2,
+            index_count: 3,
+        };
+
+        let encoded = encode_model(&model);
+        let decoded = decode_model(&encoded).unwrap();
+        assert_eq!(decoded.meshes.len(), 2);
+        assert_eq!(decoded.meshes[0].vertices[1].position, [3.0, 4.0, 5.0]);
+        assert_eq!(decoded.meshes[0].indices, vec![0, 1, 0]);
+        assert_eq!(decoded.meshes[0].material_index, Some(0));
+        assert_eq!(decoded.meshes[1].material_index, None);
+        assert_eq!(decoded.materials[0].name, "default");
+        assert_eq!(decoded.materials[0].roughness, 0.5);
+        assert_eq!(decoded.textures, model.textures);
+        assert_eq!((decoded.vertex_count, decoded.index_count), (2, 3));
+
+        assert!(decode_model(&encoded[..encoded.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn test_roughness_from_shininess() {
+        assert_eq!(roughness_from_shininess(0.0), 1.0);
+        assert!(roughness_from_shininess(1000.0) < 0.3);
+        assert!(roughness_from_shininess(10.0) > roughness_from_shininess(100.0));
+    }
+
+    #[test]
+    fn test_expand_to_rgba() {
+        assert_eq!(
+            expand_to_rgba(&[7, 9], 1, 1),
+            vec![7, 7, 7, 255, 9, 9, 9, 255]
+        );
+        assert_eq!(expand_to_rgba(&[1, 2, 3], 3, 1), vec![1, 2, 3, 255]);
+        // 16-bit channels keep their high byte
+        assert_eq!(
+            expand_to_rgba(&[0x00, 0x10, 0xff, 0x20], 2, 2),
+            vec![0x10, 0x20, 0, 255]
+        );
+    }
+
+    #[test]
+    fn test_obj_dependencies() {
+        let dir = std::env::temp_dir().join("wgpu_playground_model_deps");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("scene.obj");
+        std::fs::write(&path, "mtllib scene.mtl extra.mtl\nv 0 0 0\n").unwrap();
+
+        assert_eq!(
+            model_dependencies(&path),
+            vec![dir.join("scene.mtl"), dir.join("extra.mtl")]
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
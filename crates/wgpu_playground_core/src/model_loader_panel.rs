@@ -1,6 +1,15 @@
+use crate::asset_loader::spawn_model_load;
 use crate::assets;
-use crate::model_loader::{load_model_from_file, ModelData};
+use crate::camera::Camera;
+use crate::device_recovery::ResourceRecreate;
+use crate::geometry::Primitive;
+use crate::job_system::{Job, JobError};
+use crate::material::{bounding_sphere, GpuModel, Lighting, MaterialEditor, PbrRenderer};
+use crate::model_loader::{ModelData, ModelLoadError};
+use crate::operation_errors::OperationErrors;
+use crate::tangent_debug::TangentDebug;
 use egui::{Color32, RichText};
+use std::path::PathBuf;
 use wgpu::Device;
 
 /// UI panel for loading and managing 3D models
@@ -8,6 +17,11 @@ pub struct ModelLoaderPanel {
     // Input state
     filename_input: String,
     selected_format: ModelFormat,
+    /// Generated mesh the preview can show instead of a file
+    primitive: Primitive,
+
+    /// Import running in the background, with the filename it was asked for
+    pending_load: Option<(String, Job<Result<ModelData, ModelLoadError>>)>,
 
     // Loaded model data
     current_model: Option<ModelData>,
@@ -16,19 +30,102 @@ pub struct ModelLoaderPanel {
 
     // UI feedback
     status_message: Option<StatusMessage>,
+    /// Device errors from uploading the model
+    gpu_errors: OperationErrors,
+
+    // PBR preview of the loaded model
+    preview: Option<ModelPreview>,
+    /// Why the preview couldn't be created, so it isn't retried every frame
+    preview_error: Option<String>,
+    /// egui's handle for the preview image, freed when the preview is rebuilt
+    preview_texture_id: Option<egui::TextureId>,
+    camera: Camera,
+    lighting: Lighting,
+    material_editor: MaterialEditor,
+    /// Normal and tangent views of the preview
+    tangent_debug: TangentDebug,
+}
+
+/// Side of the square preview image, in pixels
+const PREVIEW_SIZE: u32 = 384;
+
+/// The loaded model on the GPU, rendered with its PBR materials
+struct ModelPreview {
+    renderer: PbrRenderer,
+    model: GpuModel,
+    view: wgpu::TextureView,
+}
+
+impl ModelPreview {
+    fn new(
+        device: &Device,
+        queue: &wgpu::Queue,
+        model: &ModelData,
+    ) -> Result<Self, ModelLoadError> {
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+        let renderer = PbrRenderer::new(device, queue, format);
+        let model = renderer.upload(device, queue, model)?;
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Model Preview Texture"),
+            size: wgpu::Extent3d {
+                width: PREVIEW_SIZE,
+                height: PREVIEW_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        Ok(Self {
+            renderer,
+            model,
+            view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+        })
+    }
 }
 
+/// Operation name of model buffer creation in [`OperationErrors`]
+const CREATE_BUFFERS: &str = "create_buffers";
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ModelFormat {
     Obj,
     Gltf,
+    Ply,
+    Stl,
 }
 
 impl ModelFormat {
+    const ALL: [ModelFormat; 4] = [
+        ModelFormat::Obj,
+        ModelFormat::Gltf,
+        ModelFormat::Ply,
+        ModelFormat::Stl,
+    ];
+
     fn name(&self) -> &'static str {
         match self {
             ModelFormat::Obj => "Wavefront OBJ (.obj)",
             ModelFormat::Gltf => "glTF 2.0 (.gltf/.glb)",
+            ModelFormat::Ply => "Stanford PLY (.ply)",
+            ModelFormat::Stl => "Binary STL (.stl)",
+        }
+    }
+
+    /// Format of a file, by its extension
+    fn for_path(path: &std::path::Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("obj") => ModelFormat::Obj,
+            Some("ply") => ModelFormat::Ply,
+            Some("stl") => ModelFormat::Stl,
+            _ => ModelFormat::Gltf,
         }
     }
 }
@@ -49,19 +146,135 @@ impl ModelLoaderPanel {
         Self {
             filename_input: String::new(),
             selected_format: ModelFormat::Obj,
+            primitive: Primitive::ALL[0],
+            pending_load: None,
             current_model: None,
             vertex_buffer: None,
             index_buffer: None,
             status_message: None,
+            gpu_errors: OperationErrors::new(),
+            preview: None,
+            preview_error: None,
+            preview_texture_id: None,
+            camera: Camera::new(),
+            lighting: Lighting::default(),
+            material_editor: MaterialEditor::new(),
+            tangent_debug: TangentDebug::default(),
         }
     }
 
     /// Display the model loader panel UI
     pub fn show(&mut self, ui: &mut egui::Ui, device: &Device) {
+        self.show_loader(ui, device);
+        self.show_loaded_model(ui);
+        Self::show_help(ui);
+    }
+
+    /// Display the panel with a lit preview of the loaded model and an
+    /// editor for its materials
+    ///
+    /// Note: This method is only available when building for native targets.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ui_with_preview(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        self.show_loader(ui, device);
+        if let Some(model) = &self.current_model {
+            if self.preview.is_none() && self.preview_error.is_none() {
+                if let Some(id) = self.preview_texture_id.take() {
+                    renderer.free_texture(&id);
+                }
+                match ModelPreview::new(device, queue, model) {
+                    Ok(preview) => self.preview = Some(preview),
+                    Err(e) => self.preview_error = Some(e.to_string()),
+                }
+            }
+        }
+        if let Some(error) = &self.preview_error {
+            ui.colored_label(
+                Color32::from_rgb(255, 100, 100),
+                format!("Preview unavailable: {}", error),
+            );
+        }
+        if let Some(preview) = &mut self.preview {
+            ui.separator();
+            ui.heading("🎨 Preview & Materials");
+            ui.add_space(5.0);
+            ui.horizontal_top(|ui| {
+                preview.renderer.update(
+                    queue,
+                    &self.camera,
+                    1.0,
+                    &crate::math_utils::MAT4_IDENTITY,
+                    &self.lighting,
+                    &self.tangent_debug,
+                );
+                preview.renderer.render(
+                    device,
+                    queue,
+                    &preview.model,
+                    &preview.view,
+                    (PREVIEW_SIZE, PREVIEW_SIZE),
+                    wgpu::Color {
+                        r: 0.02,
+                        g: 0.02,
+                        b: 0.03,
+                        a: 1.0,
+                    },
+                );
+                let texture_id = *self.preview_texture_id.get_or_insert_with(|| {
+                    renderer.register_native_texture(
+                        device,
+                        &preview.view,
+                        egui_wgpu::wgpu::FilterMode::Linear,
+                    )
+                });
+                ui.vertical(|ui| {
+                    let size = PREVIEW_SIZE as f32 / ui.ctx().pixels_per_point();
+                    let response = ui.add(
+                        egui::Image::new(egui::load::SizedTexture::new(
+                            texture_id,
+                            egui::vec2(size, size),
+                        ))
+                        .sense(egui::Sense::click_and_drag()),
+                    );
+                    self.camera.handle_input(ui, &response);
+                    ui.weak(self.camera.help_text());
+                });
+                ui.vertical(|ui| {
+                    ui.group(|ui| {
+                        ui.label(RichText::new("Material").strong());
+                        self.material_editor.ui(ui, &mut preview.model);
+                    });
+                    ui.group(|ui| {
+                        ui.label(RichText::new("Lighting").strong());
+                        self.lighting.ui(ui);
+                    });
+                    ui.group(|ui| {
+                        ui.label(RichText::new("Debug View").strong());
+                        self.tangent_debug.ui(ui);
+                    });
+                });
+            });
+            ui.add_space(10.0);
+        }
+        self.show_loaded_model(ui);
+        Self::show_help(ui);
+    }
+
+    /// File selection, load button and status
+    fn show_loader(&mut self, ui: &mut egui::Ui, device: &Device) {
+        self.poll_loading(device);
         ui.heading("3D Model Loader");
         ui.add_space(10.0);
 
-        ui.label("Load 3D models in glTF or OBJ format with support for materials and textures.");
+        ui.label(
+            "Load 3D models in glTF, OBJ, PLY or STL format with support for materials and textures.",
+        );
         ui.add_space(10.0);
 
         // File selection section
@@ -81,23 +294,66 @@ impl ModelLoaderPanel {
                 egui::ComboBox::from_id_salt("model_format")
                     .selected_text(self.selected_format.name())
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.selected_format,
-                            ModelFormat::Obj,
-                            ModelFormat::Obj.name(),
-                        );
-                        ui.selectable_value(
-                            &mut self.selected_format,
-                            ModelFormat::Gltf,
-                            ModelFormat::Gltf.name(),
-                        );
+                        for format in ModelFormat::ALL {
+                            ui.selectable_value(&mut self.selected_format, format, format.name());
+                        }
                     });
             });
 
             ui.add_space(10.0);
 
-            if ui.button("Load Model").clicked() {
-                self.load_model(device);
+            if ui
+                .add_enabled(self.pending_load.is_none(), egui::Button::new("Load Model"))
+                .clicked()
+            {
+                self.load_model();
+            }
+            if let Some((filename, job)) = &self.pending_load {
+                let status = job.status();
+                let mut cancel = false;
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(if status.running {
+                        format!("Importing {}...", filename)
+                    } else {
+                        format!("Waiting to import {}...", filename)
+                    });
+                    cancel = ui.small_button("Cancel").clicked();
+                });
+                if cancel {
+                    self.pending_load = None;
+                }
+            }
+            self.gpu_errors.ui(ui, CREATE_BUFFERS);
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.label(RichText::new("Primitive").strong());
+            ui.add_space(5.0);
+
+            egui::ComboBox::from_label("Shape")
+                .selected_text(self.primitive.name())
+                .show_ui(ui, |ui| {
+                    for primitive in Primitive::ALL {
+                        let selected = self.primitive.same_kind(primitive);
+                        if ui.selectable_label(selected, primitive.name()).clicked() && !selected {
+                            self.primitive = primitive;
+                        }
+                    }
+                });
+            self.primitive.ui(ui);
+
+            ui.add_space(5.0);
+            if ui
+                .add_enabled(
+                    self.pending_load.is_none(),
+                    egui::Button::new("Show Primitive"),
+                )
+                .clicked()
+            {
+                self.load_primitive(device);
             }
         });
 
@@ -114,8 +370,10 @@ impl ModelLoaderPanel {
             ui.colored_label(color, &status.text);
             ui.add_space(10.0);
         }
+    }
 
-        // Display loaded model information
+    /// Display loaded model information
+    fn show_loaded_model(&self, ui: &mut egui::Ui) {
         if let Some(model) = &self.current_model {
             ui.separator();
             ui.add_space(10.0);
@@ -123,26 +381,31 @@ impl ModelLoaderPanel {
         }
 
         ui.add_space(10.0);
+    }
 
-        // Help section
+    fn show_help(ui: &mut egui::Ui) {
         ui.collapsing("Help & Examples", |ui| {
             ui.label("Place your model files in the assets/models/ directory.");
             ui.add_space(5.0);
 
             ui.label(RichText::new("Supported formats:").strong());
-            ui.label("• Wavefront OBJ (.obj) - Simple mesh format with material support");
+            ui.label("• Wavefront OBJ (.obj) - Simple mesh format with materials and diffuse maps");
             ui.label("• glTF 2.0 (.gltf, .glb) - Modern format with full PBR material support");
+            ui.label("• Stanford PLY (.ply) - ASCII or binary, with optional normals and UVs");
+            ui.label("• Binary STL (.stl) - Triangle soup from CAD and 3D printing, flat shaded");
+            ui.label("Missing normals are generated and duplicate vertices are welded.");
             ui.add_space(5.0);
 
             ui.label(RichText::new("Example filenames:").strong());
             ui.label("• cube.obj");
             ui.label("• character.gltf");
             ui.label("• scene.glb");
+            ui.label("• cube.ply");
         });
     }
 
-    /// Load a model from the specified file
-    fn load_model(&mut self, device: &Device) {
+    /// Start importing the model from the specified file in the background
+    fn load_model(&mut self) {
         self.status_message = None;
 
         if self.filename_input.trim().is_empty() {
@@ -156,19 +419,90 @@ impl ModelLoaderPanel {
         // Construct the full path
         let path = assets::models_dir().join(&self.filename_input);
 
-        // Load the model
-        match load_model_from_file(&path) {
+        // Import off the UI thread, reusing processed geometry from the
+        // asset cache
+        self.pending_load = Some((self.filename_input.clone(), spawn_model_load(path)));
+    }
+
+    /// Start importing the model at `path`, which may be outside the models
+    /// directory
+    pub fn load_path(&mut self, path: PathBuf) {
+        self.status_message = None;
+        self.selected_format = ModelFormat::for_path(&path);
+        self.filename_input = path.display().to_string();
+        self.pending_load = Some((self.filename_input.clone(), spawn_model_load(path)));
+    }
+
+    /// Show the selected primitive as the model, e.g. to check the tangent
+    /// frame views on known geometry
+    pub fn load_primitive(&mut self, device: &Device) {
+        self.status_message = None;
+        let model = self.primitive.generate().to_model_data();
+        self.finish_load(device, self.primitive.name(), Ok(Ok(model)));
+    }
+
+    /// Whether a model is being imported
+    pub fn is_loading(&self) -> bool {
+        self.pending_load.is_some()
+    }
+
+    /// Upload the imported model if the background import has finished
+    fn poll_loading(&mut self, device: &Device) {
+        let Some((_, job)) = &mut self.pending_load else {
+            return;
+        };
+        let Some(outcome) = job.poll() else {
+            return;
+        };
+        if let Some((filename, _)) = self.pending_load.take() {
+            self.finish_load(device, &filename, outcome);
+        }
+    }
+
+    /// Wait for the background import and upload its result
+    pub fn finish_loading(&mut self, device: &Device) {
+        if let Some((filename, job)) = self.pending_load.take() {
+            self.finish_load(device, &filename, job.wait());
+        }
+    }
+
+    fn finish_load(
+        &mut self,
+        device: &Device,
+        filename: &str,
+        outcome: Result<Result<ModelData, ModelLoadError>, JobError>,
+    ) {
+        let result = match outcome {
+            Ok(result) => result,
+            Err(e) => {
+                self.status_message = Some(StatusMessage {
+                    text: format!("Failed to load model: {}", e),
+                    is_error: true,
+                });
+                return;
+            }
+        };
+        match result {
             Ok(model) => {
                 // Create GPU buffers
-                match model.create_buffers(device) {
+                let buffers = self
+                    .gpu_errors
+                    .scoped(device, CREATE_BUFFERS, || model.create_buffers(device));
+                match buffers {
+                    // The device rejected the upload; the error is shown inline
+                    Ok(_) if self.gpu_errors.has_error(CREATE_BUFFERS) => {}
                     Ok((vertex_buffer, index_buffer)) => {
                         self.status_message = Some(StatusMessage {
-                            text: format!("Successfully loaded model: {}", self.filename_input),
+                            text: format!("Successfully loaded model: {}", filename),
                             is_error: false,
                         });
 
                         self.vertex_buffer = Some(vertex_buffer);
                         self.index_buffer = Some(index_buffer);
+                        let (center, radius) = bounding_sphere(&model);
+                        self.camera = Camera::framing(center, radius);
+                        self.preview = None;
+                        self.preview_error = None;
                         self.current_model = Some(model);
                     }
                     Err(e) => {
@@ -223,6 +557,11 @@ impl ModelLoaderPanel {
                                     material.diffuse_color[3]
                                 ));
 
+                                ui.label(format!(
+                                    "Metallic: {:.2}, Roughness: {:.2}",
+                                    material.metallic, material.roughness
+                                ));
+
                                 if let Some(tex) = &material.diffuse_texture {
                                     ui.label(format!("Diffuse Texture: {}", tex));
                                 }
@@ -286,3 +625,34 @@ impl ModelLoaderPanel {
         self.current_model.as_ref()
     }
 }
+
+impl ResourceRecreate for ModelLoaderPanel {
+    fn recreate_resources(&mut self, device: &wgpu::Device, _queue: &wgpu::Queue) {
+        self.vertex_buffer = None;
+        self.index_buffer = None;
+        // The preview is rebuilt on the next frame that shows it
+        self.preview = None;
+        self.preview_error = None;
+        self.gpu_errors.clear_all();
+        let Some(model) = &self.current_model else {
+            return;
+        };
+        // Upload the loaded model again instead of reading the file back
+        let buffers = self
+            .gpu_errors
+            .scoped(device, CREATE_BUFFERS, || model.create_buffers(device));
+        match buffers {
+            Ok(_) if self.gpu_errors.has_error(CREATE_BUFFERS) => {}
+            Ok((vertex_buffer, index_buffer)) => {
+                self.vertex_buffer = Some(vertex_buffer);
+                self.index_buffer = Some(index_buffer);
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage {
+                    text: format!("Failed to recreate GPU buffers: {}", e),
+                    is_error: true,
+                });
+            }
+        }
+    }
+}
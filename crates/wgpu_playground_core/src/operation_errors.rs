@@ -0,0 +1,168 @@
+//! Per-operation GPU error capture for panels
+//!
+//! Panels run each resource creation or pipeline build through
+//! [`OperationErrors::scoped`], which wraps it in validation, out-of-memory
+//! and internal error scopes. Captured errors are stored under the
+//! operation's name, so the panel can show them next to the control that
+//! triggered the operation instead of them reaching the device's
+//! uncaptured error handler.
+//!
+//! On native platforms the scopes are popped before `scoped` returns, so
+//! the caller can check [`OperationErrors::get`] right away. On the web,
+//! errors arrive asynchronously and show up on a later frame.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::Error;
+
+/// GPU errors of a panel, keyed by operation
+///
+/// Cloning is cheap and clones share the same errors.
+#[derive(Clone, Default)]
+pub struct OperationErrors {
+    errors: Arc<Mutex<BTreeMap<&'static str, Error>>>,
+}
+
+impl std::fmt::Debug for OperationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.lock().iter()).finish()
+    }
+}
+
+impl OperationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<&'static str, Error>> {
+        self.errors.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Run `f` inside error scopes and attribute its errors to `operation`
+    ///
+    /// A previous error of the same operation is cleared first. Only the
+    /// first captured error is kept, since later ones are usually caused
+    /// by it (an invalid layout makes the pipeline using it invalid too).
+    pub fn scoped<T>(
+        &self,
+        device: &wgpu::Device,
+        operation: &'static str,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        self.clear(operation);
+
+        let validation = device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let out_of_memory = device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let internal = device.push_error_scope(wgpu::ErrorFilter::Internal);
+        let result = f();
+        // Scopes must be popped innermost first
+        let pops = [internal.pop(), out_of_memory.pop(), validation.pop()];
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            for pop in pops {
+                if let Some(error) = pollster::block_on(pop) {
+                    self.record(operation, error);
+                }
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let errors = self.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                for pop in pops {
+                    if let Some(error) = pop.await {
+                        errors.record(operation, error);
+                    }
+                }
+            });
+        }
+
+        result
+    }
+
+    fn record(&self, operation: &'static str, error: wgpu::Error) {
+        let error = Error::from(error);
+        log::debug!("{} failed: {}", operation, error);
+        self.lock().entry(operation).or_insert(error);
+    }
+
+    /// Attribute an error detected outside the device to `operation`,
+    /// replacing any previous one
+    pub fn insert(&self, operation: &'static str, error: Error) {
+        log::debug!("{} failed: {}", operation, error);
+        self.lock().insert(operation, error);
+    }
+
+    /// Error captured by the last run of `operation`
+    pub fn get(&self, operation: &str) -> Option<Error> {
+        self.lock().get(operation).cloned()
+    }
+
+    pub fn has_error(&self, operation: &str) -> bool {
+        self.lock().contains_key(operation)
+    }
+
+    pub fn clear(&self, operation: &str) {
+        self.lock().remove(operation);
+    }
+
+    pub fn clear_all(&self) {
+        self.lock().clear();
+    }
+
+    /// All current errors, ordered by operation name
+    pub fn errors(&self) -> Vec<(&'static str, Error)> {
+        self.lock()
+            .iter()
+            .map(|(operation, error)| (*operation, error.clone()))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Show the error of `operation`, if any, with a button to dismiss it
+    ///
+    /// Place this right below the control that runs the operation.
+    pub fn ui(&self, ui: &mut egui::Ui, operation: &str) {
+        let Some(error) = self.get(operation) else {
+            return;
+        };
+        ui.horizontal_wrapped(|ui| {
+            ui.colored_label(egui::Color32::RED, format!("❌ {}", error.message));
+            if ui.small_button("✕").on_hover_text("Dismiss").clicked() {
+                self.clear(operation);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_errors() {
+        let errors = OperationErrors::new();
+        let shared = errors.clone();
+        errors
+            .lock()
+            .insert("build", Error::validation("bad layout"));
+        assert_eq!(shared.get("build").unwrap().message, "bad layout");
+        shared.clear_all();
+        assert!(errors.errors().is_empty());
+    }
+
+    #[test]
+    fn test_insert_replaces_error() {
+        let errors = OperationErrors::new();
+        errors.insert("create", Error::validation("bad size"));
+        errors.insert("create", Error::out_of_memory("over budget"));
+        let error = errors.get("create").unwrap();
+        assert_eq!(error.error_type, crate::error::ErrorType::OutOfMemory);
+        assert_eq!(error.message, "over budget");
+    }
+}
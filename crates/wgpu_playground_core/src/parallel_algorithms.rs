@@ -0,0 +1,1097 @@
+//! Parallel reduction and prefix scan for the algorithm examples
+//!
+//! Both algorithms run over a buffer of `u32` values with one of the
+//! operators of [`ReduceOp`], in two strategies the examples time against
+//! each other and against the CPU:
+//!
+//! - [`Strategy::Workgroup`] combines values in `var<workgroup>` memory,
+//!   synchronized with `workgroupBarrier`, so each pass over global memory
+//!   shrinks the data by a whole workgroup. Reduction repeats the pass over
+//!   the partial results until one value is left; scan scans the blocks,
+//!   scans the block totals recursively and adds them back
+//! - [`Strategy::Naive`] works on one element per invocation in global
+//!   memory: reduction folds every element into a single atomic, scan runs
+//!   one Hillis-Steele step per dispatch, `log2(count)` dispatches in all
+//!
+//! Every result is read back and checked against [`cpu_reference`].
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use std::time::{Duration, Instant};
+
+/// Invocations per workgroup of every kernel
+pub const WORKGROUP_SIZE: u32 = 256;
+
+/// Elements a reduction workgroup combines, two per invocation
+const REDUCE_ELEMENTS_PER_WORKGROUP: u32 = WORKGROUP_SIZE * 2;
+
+/// Fewest elements the examples run on
+pub const MIN_ELEMENTS: u32 = 1 << 10;
+
+/// Most elements the examples run on
+///
+/// One invocation per element stays within the 65535 workgroups a single
+/// dispatch dimension allows, and the sum of [`random_input`] within a `u32`.
+pub const MAX_ELEMENTS: u32 = 1 << 22;
+
+/// Largest value of [`random_input`]
+const MAX_INPUT_VALUE: u32 = 1000;
+
+pub const REDUCTION_SHADER: &str = r#"// Parallel Reduction Example
+// `reduce` combines 512 elements per workgroup in shared memory into one
+// partial result; the host repeats it over the partial results until one is
+// left. `reduce_naive` folds every element straight into a single atomic.
+
+struct Params {
+    count: u32,
+    op: u32,
+    offset: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+const OP_SUM: u32 = 0u;
+const OP_MIN: u32 = 1u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> input: array<u32>;
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+@group(0) @binding(3) var<storage, read_write> total: atomic<u32>;
+
+var<workgroup> partial: array<u32, WORKGROUP_SIZE>;
+
+fn identity() -> u32 {
+    switch params.op {
+        case OP_SUM: { return 0u; }
+        case OP_MIN: { return 0xffffffffu; }
+        default: { return 0u; }
+    }
+}
+
+fn combine(a: u32, b: u32) -> u32 {
+    switch params.op {
+        case OP_SUM: { return a + b; }
+        case OP_MIN: { return min(a, b); }
+        default: { return max(a, b); }
+    }
+}
+
+fn load(index: u32) -> u32 {
+    if index < params.count {
+        return input[index];
+    }
+    return identity();
+}
+
+@compute @workgroup_size(256)
+fn reduce(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) group_id: vec3<u32>,
+) {
+    let local = local_id.x;
+    // Every invocation combines two elements on the way in
+    let first = group_id.x * WORKGROUP_SIZE * 2u + local;
+    partial[local] = combine(load(first), load(first + WORKGROUP_SIZE));
+    workgroupBarrier();
+
+    // Halve the active invocations each step; the barrier makes one step's
+    // writes visible before the next step reads them
+    for (var stride = WORKGROUP_SIZE / 2u; stride > 0u; stride /= 2u) {
+        if local < stride {
+            partial[local] = combine(partial[local], partial[local + stride]);
+        }
+        workgroupBarrier();
+    }
+
+    if local == 0u {
+        output[group_id.x] = partial[0];
+    }
+}
+
+@compute @workgroup_size(256)
+fn reduce_naive(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= params.count {
+        return;
+    }
+    // Every invocation contends for the same atomic
+    let value = input[id.x];
+    switch params.op {
+        case OP_SUM: { atomicAdd(&total, value); }
+        case OP_MIN: { atomicMin(&total, value); }
+        default: { atomicMax(&total, value); }
+    }
+}
+"#;
+
+pub const PREFIX_SCAN_SHADER: &str = r#"// Prefix Scan Example
+// An inclusive scan in three steps:
+// 1. scan_blocks scans 256 elements per workgroup in shared memory and
+//    writes the total of each block to block_sums
+// 2. the host scans block_sums the same way, recursively, until a single
+//    workgroup covers them
+// 3. add_block_offsets combines every element with the scanned total of the
+//    blocks before its own
+// `scan_naive` is one Hillis-Steele step over global memory per dispatch;
+// the host runs it log2(count) times with a doubling offset.
+
+struct Params {
+    count: u32,
+    op: u32,
+    offset: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+const OP_SUM: u32 = 0u;
+const OP_MIN: u32 = 1u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> input: array<u32>;
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+@group(0) @binding(3) var<storage, read_write> block_sums: array<u32>;
+
+var<workgroup> scanned: array<u32, WORKGROUP_SIZE>;
+
+fn identity() -> u32 {
+    switch params.op {
+        case OP_SUM: { return 0u; }
+        case OP_MIN: { return 0xffffffffu; }
+        default: { return 0u; }
+    }
+}
+
+fn combine(a: u32, b: u32) -> u32 {
+    switch params.op {
+        case OP_SUM: { return a + b; }
+        case OP_MIN: { return min(a, b); }
+        default: { return max(a, b); }
+    }
+}
+
+@compute @workgroup_size(256)
+fn scan_blocks(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) group_id: vec3<u32>,
+) {
+    let local = local_id.x;
+    let index = group_id.x * WORKGROUP_SIZE + local;
+    var value = identity();
+    if index < params.count {
+        value = input[index];
+    }
+    scanned[local] = value;
+    workgroupBarrier();
+
+    // Hillis-Steele: after the step with `offset`, every element holds the
+    // combination of the 2 * offset elements ending at it
+    for (var offset = 1u; offset < WORKGROUP_SIZE; offset *= 2u) {
+        if local >= offset {
+            value = combine(scanned[local - offset], value);
+        }
+        // Every read of this step happens before any write
+        workgroupBarrier();
+        scanned[local] = value;
+        workgroupBarrier();
+    }
+
+    if index < params.count {
+        output[index] = value;
+    }
+    if local == WORKGROUP_SIZE - 1u {
+        block_sums[group_id.x] = value;
+    }
+}
+
+@compute @workgroup_size(256)
+fn add_block_offsets(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(workgroup_id) group_id: vec3<u32>,
+) {
+    if group_id.x == 0u || id.x >= params.count {
+        return;
+    }
+    output[id.x] = combine(block_sums[group_id.x - 1u], output[id.x]);
+}
+
+@compute @workgroup_size(256)
+fn scan_naive(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= params.count {
+        return;
+    }
+    var value = input[id.x];
+    if id.x >= params.offset {
+        value = combine(input[id.x - params.offset], value);
+    }
+    output[id.x] = value;
+}
+"#;
+
+/// An algorithm of the algorithm examples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Reduction,
+    PrefixScan,
+}
+
+impl Algorithm {
+    pub fn all() -> &'static [Algorithm] {
+        &[Algorithm::Reduction, Algorithm::PrefixScan]
+    }
+
+    /// Id of the example showing this algorithm
+    pub fn example_id(&self) -> &'static str {
+        match self {
+            Algorithm::Reduction => "parallel_reduction",
+            Algorithm::PrefixScan => "prefix_scan",
+        }
+    }
+
+    pub fn from_example_id(id: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|algorithm| algorithm.example_id() == id)
+    }
+
+    pub fn shader(&self) -> &'static str {
+        match self {
+            Algorithm::Reduction => REDUCTION_SHADER,
+            Algorithm::PrefixScan => PREFIX_SCAN_SHADER,
+        }
+    }
+}
+
+/// The associative operator an algorithm combines elements with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReduceOp {
+    Sum,
+    Min,
+    Max,
+}
+
+impl ReduceOp {
+    pub fn all() -> &'static [ReduceOp] {
+        &[ReduceOp::Sum, ReduceOp::Min, ReduceOp::Max]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReduceOp::Sum => "Sum",
+            ReduceOp::Min => "Min",
+            ReduceOp::Max => "Max",
+        }
+    }
+
+    /// The value combining with any `x` to `x`
+    pub fn identity(&self) -> u32 {
+        match self {
+            ReduceOp::Sum | ReduceOp::Max => 0,
+            ReduceOp::Min => u32::MAX,
+        }
+    }
+
+    /// Combine two values, wrapping on overflow like WGSL
+    pub fn apply(&self, a: u32, b: u32) -> u32 {
+        match self {
+            ReduceOp::Sum => a.wrapping_add(b),
+            ReduceOp::Min => a.min(b),
+            ReduceOp::Max => a.max(b),
+        }
+    }
+
+    /// Matches the `OP_` constants of the shaders
+    fn shader_value(&self) -> u32 {
+        match self {
+            ReduceOp::Sum => 0,
+            ReduceOp::Min => 1,
+            ReduceOp::Max => 2,
+        }
+    }
+}
+
+/// How the GPU runs an algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Combine in workgroup shared memory
+    Workgroup,
+    /// One element per invocation in global memory
+    Naive,
+}
+
+impl Strategy {
+    pub fn all() -> &'static [Strategy] {
+        &[Strategy::Workgroup, Strategy::Naive]
+    }
+
+    pub fn label(&self, algorithm: Algorithm) -> &'static str {
+        match (self, algorithm) {
+            (Strategy::Workgroup, _) => "Workgroup shared memory",
+            (Strategy::Naive, Algorithm::Reduction) => "Naive (global atomic)",
+            (Strategy::Naive, Algorithm::PrefixScan) => "Naive (Hillis-Steele)",
+        }
+    }
+}
+
+/// Adjustable parameters of the algorithm examples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlgorithmParams {
+    /// Number of input elements, from [`MIN_ELEMENTS`] to [`MAX_ELEMENTS`]
+    pub element_count: u32,
+    pub op: ReduceOp,
+}
+
+impl Default for AlgorithmParams {
+    fn default() -> Self {
+        Self {
+            element_count: 1 << 20,
+            op: ReduceOp::Sum,
+        }
+    }
+}
+
+/// Pseudo-random values from 1 to 1000, the same for the same `seed`
+pub fn random_input(count: u32, seed: u32) -> Vec<u32> {
+    // xorshift32, which never leaves zero
+    let mut state = seed.max(1);
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state % MAX_INPUT_VALUE + 1
+        })
+        .collect()
+}
+
+/// The result the GPU must produce: one value for a reduction, one per
+/// element for an inclusive scan
+pub fn cpu_reference(algorithm: Algorithm, op: ReduceOp, input: &[u32]) -> Vec<u32> {
+    match algorithm {
+        Algorithm::Reduction => vec![input
+            .iter()
+            .fold(op.identity(), |acc, &value| op.apply(acc, value))],
+        Algorithm::PrefixScan => input
+            .iter()
+            .scan(op.identity(), |acc, &value| {
+                *acc = op.apply(*acc, value);
+                Some(*acc)
+            })
+            .collect(),
+    }
+}
+
+/// Index of the first value of `actual` that differs from `expected`
+pub fn first_mismatch(expected: &[u32], actual: &[u32]) -> Option<usize> {
+    expected
+        .iter()
+        .zip(actual)
+        .position(|(e, a)| e != a)
+        .or_else(|| (expected.len() != actual.len()).then(|| expected.len().min(actual.len())))
+}
+
+/// Matches the `Params` struct of the shaders
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bytemuck::Pod, bytemuck::Zeroable)]
+struct ParamsUniform {
+    count: u32,
+    op: u32,
+    offset: u32,
+    _padding: u32,
+}
+
+/// A compute entry point of the shaders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kernel {
+    Reduce,
+    ReduceNaive,
+    ScanBlocks,
+    AddBlockOffsets,
+    ScanNaive,
+}
+
+/// One dispatch, binding `input`, `output` and `extra` of the plan's buffers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Step {
+    kernel: Kernel,
+    params: ParamsUniform,
+    input: usize,
+    output: usize,
+    extra: usize,
+    workgroups: u32,
+}
+
+/// The buffers and dispatches of one run
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Plan {
+    /// Length in elements of each buffer; buffer 0 is the input
+    buffer_lens: Vec<u32>,
+    steps: Vec<Step>,
+    /// Buffer set to the identity before the first step
+    reset: Option<usize>,
+    /// Buffer holding the result
+    result: usize,
+}
+
+impl Plan {
+    fn new(algorithm: Algorithm, strategy: Strategy, op: ReduceOp, count: u32) -> Self {
+        let mut plan = Plan {
+            buffer_lens: vec![count],
+            steps: Vec::new(),
+            reset: None,
+            result: 0,
+        };
+        let params = |count, offset| ParamsUniform {
+            count,
+            op: op.shader_value(),
+            offset,
+            _padding: 0,
+        };
+        match (algorithm, strategy) {
+            (Algorithm::Reduction, Strategy::Workgroup) => {
+                let unused = plan.buffer(1);
+                let (mut input, mut count) = (0, count);
+                loop {
+                    let workgroups = count.div_ceil(REDUCE_ELEMENTS_PER_WORKGROUP);
+                    let output = plan.buffer(workgroups);
+                    plan.steps.push(Step {
+                        kernel: Kernel::Reduce,
+                        params: params(count, 0),
+                        input,
+                        output,
+                        extra: unused,
+                        workgroups,
+                    });
+                    (input, count) = (output, workgroups);
+                    if count == 1 {
+                        break;
+                    }
+                }
+                plan.result = input;
+            }
+            (Algorithm::Reduction, Strategy::Naive) => {
+                let total = plan.buffer(1);
+                let unused = plan.buffer(1);
+                plan.reset = Some(total);
+                plan.steps.push(Step {
+                    kernel: Kernel::ReduceNaive,
+                    params: params(count, 0),
+                    input: 0,
+                    output: unused,
+                    extra: total,
+                    workgroups: count.div_ceil(WORKGROUP_SIZE),
+                });
+                plan.result = total;
+            }
+            (Algorithm::PrefixScan, Strategy::Workgroup) => {
+                // (input, output, count) of every level
+                let mut levels = Vec::new();
+                let (mut input, mut count) = (0, count);
+                loop {
+                    let workgroups = count.div_ceil(WORKGROUP_SIZE);
+                    let output = plan.buffer(count);
+                    let block_sums = plan.buffer(workgroups);
+                    plan.steps.push(Step {
+                        kernel: Kernel::ScanBlocks,
+                        params: params(count, 0),
+                        input,
+                        output,
+                        extra: block_sums,
+                        workgroups,
+                    });
+                    levels.push((input, output, count));
+                    if workgroups == 1 {
+                        break;
+                    }
+                    (input, count) = (block_sums, workgroups);
+                }
+                for pair in levels.windows(2).rev() {
+                    let (input, output, count) = pair[0];
+                    plan.steps.push(Step {
+                        kernel: Kernel::AddBlockOffsets,
+                        params: params(count, 0),
+                        input,
+                        output,
+                        extra: pair[1].1,
+                        workgroups: count.div_ceil(WORKGROUP_SIZE),
+                    });
+                }
+                plan.result = levels[0].1;
+            }
+            (Algorithm::PrefixScan, Strategy::Naive) => {
+                let ping_pong = [plan.buffer(count), plan.buffer(count)];
+                let unused = plan.buffer(1);
+                let mut input = 0;
+                let mut offset = 1;
+                while offset < count {
+                    let output = ping_pong[plan.steps.len() % 2];
+                    plan.steps.push(Step {
+                        kernel: Kernel::ScanNaive,
+                        params: params(count, offset),
+                        input,
+                        output,
+                        extra: unused,
+                        workgroups: count.div_ceil(WORKGROUP_SIZE),
+                    });
+                    input = output;
+                    offset *= 2;
+                }
+                plan.result = input;
+            }
+        }
+        plan
+    }
+
+    /// Add a buffer of `len` elements, returning its index
+    fn buffer(&mut self, len: u32) -> usize {
+        self.buffer_lens.push(len);
+        self.buffer_lens.len() - 1
+    }
+}
+
+/// Output and timing of one GPU run
+#[derive(Debug, Clone)]
+pub struct AlgorithmRun {
+    pub output: Vec<u32>,
+    /// From submitting the dispatches until the GPU finished them
+    pub gpu_time: Duration,
+    pub dispatches: usize,
+}
+
+/// Timing and verification of one strategy in a [`Comparison`]
+#[derive(Debug, Clone)]
+pub struct StrategyResult {
+    pub strategy: Strategy,
+    pub gpu_time: Duration,
+    pub dispatches: usize,
+    /// First element that differs from the CPU result, `None` if all match
+    pub mismatch: Option<usize>,
+}
+
+/// Both strategies timed against the CPU on the same input
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub algorithm: Algorithm,
+    pub params: AlgorithmParams,
+    pub cpu_time: Duration,
+    /// The reduced value, or the last element of the scan
+    pub result: u32,
+    pub strategies: Vec<StrategyResult>,
+}
+
+impl Comparison {
+    /// How many times faster the workgroup strategy ran than the naive one
+    pub fn speedup(&self) -> Option<f64> {
+        let time = |strategy| {
+            self.strategies
+                .iter()
+                .find(|result| result.strategy == strategy)
+                .map(|result| result.gpu_time.as_secs_f64())
+        };
+        match (time(Strategy::Workgroup), time(Strategy::Naive)) {
+            (Some(fast), Some(slow)) if fast > 0.0 => Some(slow / fast),
+            _ => None,
+        }
+    }
+
+    /// Whether every strategy matched the CPU
+    pub fn verified(&self) -> bool {
+        self.strategies
+            .iter()
+            .all(|result| result.mismatch.is_none())
+    }
+}
+
+/// The uploaded input of the runs
+struct InputBuffer {
+    buffer: wgpu::Buffer,
+    count: u32,
+}
+
+/// Pipelines for running the algorithms on one input buffer
+pub struct AlgorithmRunner {
+    layout: wgpu::BindGroupLayout,
+    reduce: wgpu::ComputePipeline,
+    reduce_naive: wgpu::ComputePipeline,
+    scan_blocks: wgpu::ComputePipeline,
+    add_block_offsets: wgpu::ComputePipeline,
+    scan_naive: wgpu::ComputePipeline,
+    input: Option<InputBuffer>,
+}
+
+impl AlgorithmRunner {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        let storage_buffer_entry = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Parallel Algorithms Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_buffer_entry(1, true),
+                storage_buffer_entry(2, false),
+                storage_buffer_entry(3, false),
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Parallel Algorithms Pipeline Layout"),
+            bind_group_layouts: &[Some(&layout)],
+            immediate_size: 0,
+        });
+
+        let create_module = |label, source: &'static str| {
+            tracker.record(ApiCategory::Shader, "create_shader_module");
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            })
+        };
+        let reduction_module = create_module("Parallel Reduction Shader", REDUCTION_SHADER);
+        let scan_module = create_module("Prefix Scan Shader", PREFIX_SCAN_SHADER);
+
+        let create_pipeline = |module: &wgpu::ShaderModule, entry_point| {
+            tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+
+        Self {
+            reduce: create_pipeline(&reduction_module, "reduce"),
+            reduce_naive: create_pipeline(&reduction_module, "reduce_naive"),
+            scan_blocks: create_pipeline(&scan_module, "scan_blocks"),
+            add_block_offsets: create_pipeline(&scan_module, "add_block_offsets"),
+            scan_naive: create_pipeline(&scan_module, "scan_naive"),
+            layout,
+            input: None,
+        }
+    }
+
+    fn pipeline(&self, kernel: Kernel) -> &wgpu::ComputePipeline {
+        match kernel {
+            Kernel::Reduce => &self.reduce,
+            Kernel::ReduceNaive => &self.reduce_naive,
+            Kernel::ScanBlocks => &self.scan_blocks,
+            Kernel::AddBlockOffsets => &self.add_block_offsets,
+            Kernel::ScanNaive => &self.scan_naive,
+        }
+    }
+
+    /// Upload the values the algorithms run on
+    pub fn set_input(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        values: &[u32],
+    ) -> Result<(), String> {
+        let count = values.len() as u32;
+        if !(MIN_ELEMENTS..=MAX_ELEMENTS).contains(&count) {
+            return Err(format!(
+                "Element count {} is outside {}..={}",
+                count, MIN_ELEMENTS, MAX_ELEMENTS
+            ));
+        }
+        let size = count as u64 * 4;
+        let max = device.limits().max_storage_buffer_binding_size;
+        if size > max {
+            return Err(format!(
+                "{} elements need {} bytes, more than the device's storage binding limit of {}",
+                count, size, max
+            ));
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Parallel Algorithms Input"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        tracker.record(ApiCategory::Queue, "write_buffer");
+        queue.write_buffer(&buffer, 0, bytemuck::cast_slice(values));
+        self.input = Some(InputBuffer { buffer, count });
+        Ok(())
+    }
+
+    /// Number of uploaded elements
+    pub fn element_count(&self) -> Option<u32> {
+        self.input.as_ref().map(|input| input.count)
+    }
+
+    /// Run `algorithm` on the uploaded input and read the result back
+    ///
+    /// Only the dispatches are timed, not creating their buffers or
+    /// reading the result back.
+    pub fn run(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        algorithm: Algorithm,
+        strategy: Strategy,
+        op: ReduceOp,
+    ) -> Result<AlgorithmRun, String> {
+        let input = self.input.as_ref().ok_or("No input is uploaded")?;
+        let plan = Plan::new(algorithm, strategy, op, input.count);
+        let tracker = ApiCoverageTracker::global();
+
+        let buffers: Vec<wgpu::Buffer> = plan
+            .buffer_lens
+            .iter()
+            .enumerate()
+            .map(|(index, &len)| {
+                if index == 0 {
+                    return input.buffer.clone();
+                }
+                tracker.record(ApiCategory::Buffer, "create_buffer");
+                device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Parallel Algorithms Scratch"),
+                    size: len as u64 * 4,
+                    usage: wgpu::BufferUsages::STORAGE
+                        | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                })
+            })
+            .collect();
+
+        // One uniform slot per step, each at an aligned offset
+        let params_size = std::mem::size_of::<ParamsUniform>() as u64;
+        let stride = params_size
+            .next_multiple_of(device.limits().min_uniform_buffer_offset_alignment as u64);
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Parallel Algorithms Params"),
+            size: stride * plan.steps.len().max(1) as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_groups: Vec<wgpu::BindGroup> = plan
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                tracker.record(ApiCategory::Queue, "write_buffer");
+                queue.write_buffer(
+                    &params,
+                    stride * index as u64,
+                    bytemuck::bytes_of(&step.params),
+                );
+                tracker.record(ApiCategory::BindGroup, "create_bind_group");
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Parallel Algorithms Bind Group"),
+                    layout: &self.layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                buffer: &params,
+                                offset: stride * index as u64,
+                                size: wgpu::BufferSize::new(params_size),
+                            }),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: buffers[step.input].as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: buffers[step.output].as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: buffers[step.extra].as_entire_binding(),
+                        },
+                    ],
+                })
+            })
+            .collect();
+        if let Some(reset) = plan.reset {
+            queue.write_buffer(&buffers[reset], 0, bytemuck::bytes_of(&op.identity()));
+        }
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Parallel Algorithms Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Parallel Algorithms Pass"),
+                timestamp_writes: None,
+            });
+            for (step, bind_group) in plan.steps.iter().zip(&bind_groups) {
+                tracker.record(ApiCategory::ComputePass, "set_pipeline");
+                pass.set_pipeline(self.pipeline(step.kernel));
+                tracker.record(ApiCategory::ComputePass, "set_bind_group");
+                pass.set_bind_group(0, bind_group, &[]);
+                tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+                pass.dispatch_workgroups(step.workgroups, 1, 1);
+            }
+        }
+        // Flush the uploads above so they are not part of the timed submission
+        queue.submit(std::iter::empty());
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+
+        let start = Instant::now();
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(std::iter::once(encoder.finish()));
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+        let gpu_time = start.elapsed();
+
+        let output = read_buffer(device, queue, &buffers[plan.result])?;
+        let len = match algorithm {
+            Algorithm::Reduction => 1,
+            Algorithm::PrefixScan => input.count as usize,
+        };
+        Ok(AlgorithmRun {
+            output: output[..len].to_vec(),
+            gpu_time,
+            dispatches: plan.steps.len(),
+        })
+    }
+
+    /// Upload [`random_input`] and time every strategy against the CPU
+    pub fn compare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        algorithm: Algorithm,
+        params: AlgorithmParams,
+    ) -> Result<Comparison, String> {
+        let count = params.element_count.clamp(MIN_ELEMENTS, MAX_ELEMENTS);
+        let input = random_input(count, count);
+        self.set_input(device, queue, &input)?;
+
+        let start = Instant::now();
+        let expected = cpu_reference(algorithm, params.op, &input);
+        let cpu_time = start.elapsed();
+
+        let strategies = Strategy::all()
+            .iter()
+            .map(|&strategy| {
+                let run = self.run(device, queue, algorithm, strategy, params.op)?;
+                Ok(StrategyResult {
+                    strategy,
+                    gpu_time: run.gpu_time,
+                    dispatches: run.dispatches,
+                    mismatch: first_mismatch(&expected, &run.output),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Comparison {
+            algorithm,
+            params: AlgorithmParams {
+                element_count: count,
+                ..params
+            },
+            cpu_time,
+            result: expected.last().copied().unwrap_or_default(),
+            strategies,
+        })
+    }
+}
+
+/// Copy `buffer` into a mappable one and read it
+fn read_buffer(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    buffer: &wgpu::Buffer,
+) -> Result<Vec<u32>, String> {
+    let tracker = ApiCoverageTracker::global();
+    tracker.record(ApiCategory::Buffer, "create_buffer");
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Parallel Algorithms Readback"),
+        size: buffer.size(),
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Parallel Algorithms Readback Encoder"),
+    });
+    tracker.record(ApiCategory::CommandEncoder, "copy_buffer_to_buffer");
+    encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+    tracker.record(ApiCategory::Queue, "submit");
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = staging.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    tracker.record(ApiCategory::Buffer, "map_async");
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    let _ = device.poll(wgpu::PollType::wait_indefinitely());
+    match receiver.recv() {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return Err(format!("Failed to read the result: {}", e)),
+        Err(_) => return Err("Failed to read the result".to_string()),
+    }
+    let values = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+    staging.unmap();
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_validate() {
+        for algorithm in Algorithm::all() {
+            validate(algorithm.shader());
+        }
+    }
+
+    #[test]
+    fn test_example_ids_round_trip() {
+        for algorithm in Algorithm::all() {
+            assert_eq!(
+                Algorithm::from_example_id(algorithm.example_id()),
+                Some(*algorithm)
+            );
+        }
+        assert_eq!(Algorithm::from_example_id("gaussian_blur"), None);
+    }
+
+    #[test]
+    fn test_params_uniform_layout() {
+        assert_eq!(std::mem::size_of::<ParamsUniform>(), 16);
+    }
+
+    #[test]
+    fn test_cpu_reference() {
+        let input = [3, 1, 4, 1, 5];
+        assert_eq!(
+            cpu_reference(Algorithm::Reduction, ReduceOp::Sum, &input),
+            [14]
+        );
+        assert_eq!(
+            cpu_reference(Algorithm::Reduction, ReduceOp::Min, &input),
+            [1]
+        );
+        assert_eq!(
+            cpu_reference(Algorithm::Reduction, ReduceOp::Max, &input),
+            [5]
+        );
+        assert_eq!(
+            cpu_reference(Algorithm::PrefixScan, ReduceOp::Sum, &input),
+            [3, 4, 8, 9, 14]
+        );
+        assert_eq!(
+            cpu_reference(Algorithm::PrefixScan, ReduceOp::Max, &input),
+            [3, 3, 4, 4, 5]
+        );
+    }
+
+    #[test]
+    fn test_random_input_sum_fits_u32() {
+        let input = random_input(4096, 7);
+        assert_eq!(input, random_input(4096, 7));
+        assert!(input.iter().all(|&v| (1..=MAX_INPUT_VALUE).contains(&v)));
+        assert!((MAX_ELEMENTS as u64) * (MAX_INPUT_VALUE as u64) <= u32::MAX as u64);
+    }
+
+    #[test]
+    fn test_first_mismatch() {
+        assert_eq!(first_mismatch(&[1, 2, 3], &[1, 2, 3]), None);
+        assert_eq!(first_mismatch(&[1, 2, 3], &[1, 5, 3]), Some(1));
+        assert_eq!(first_mismatch(&[1, 2, 3], &[1, 2]), Some(2));
+    }
+
+    #[test]
+    fn test_reduction_plan_repeats_until_one_value() {
+        let plan = Plan::new(
+            Algorithm::Reduction,
+            Strategy::Workgroup,
+            ReduceOp::Sum,
+            1 << 20,
+        );
+        let workgroups: Vec<u32> = plan.steps.iter().map(|s| s.workgroups).collect();
+        assert_eq!(workgroups, [2048, 4, 1]);
+        assert_eq!(plan.buffer_lens[plan.result], 1);
+        // Every pass reads the output of the previous one
+        for pair in plan.steps.windows(2) {
+            assert_eq!(pair[1].input, pair[0].output);
+        }
+
+        let plan = Plan::new(
+            Algorithm::Reduction,
+            Strategy::Naive,
+            ReduceOp::Min,
+            1 << 20,
+        );
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.reset, Some(plan.result));
+    }
+
+    #[test]
+    fn test_scan_plan_adds_block_offsets_top_down() {
+        let plan = Plan::new(
+            Algorithm::PrefixScan,
+            Strategy::Workgroup,
+            ReduceOp::Sum,
+            1 << 20,
+        );
+        let kernels: Vec<Kernel> = plan.steps.iter().map(|s| s.kernel).collect();
+        assert_eq!(
+            kernels,
+            [
+                Kernel::ScanBlocks,
+                Kernel::ScanBlocks,
+                Kernel::ScanBlocks,
+                Kernel::AddBlockOffsets,
+                Kernel::AddBlockOffsets,
+            ]
+        );
+        // The finest level is fixed up last, from the scanned level above
+        let last = plan.steps[4];
+        assert_eq!(last.output, plan.result);
+        assert_eq!(last.extra, plan.steps[1].output);
+        assert_eq!(plan.buffer_lens[plan.result], 1 << 20);
+    }
+
+    #[test]
+    fn test_naive_scan_plan_doubles_offset() {
+        let plan = Plan::new(Algorithm::PrefixScan, Strategy::Naive, ReduceOp::Sum, 1000);
+        let offsets: Vec<u32> = plan.steps.iter().map(|s| s.params.offset).collect();
+        assert_eq!(offsets, [1, 2, 4, 8, 16, 32, 64, 128, 256, 512]);
+        for pair in plan.steps.windows(2) {
+            assert_eq!(pair[1].input, pair[0].output);
+            assert_ne!(pair[1].output, pair[0].output);
+        }
+        assert_eq!(plan.result, plan.steps.last().unwrap().output);
+    }
+}
@@ -1,5 +1,7 @@
 /// Performance metrics panel UI
+use crate::asset_cache::format_bytes;
 use crate::performance_metrics::PerformanceMetrics;
+use crate::upload_belt::UploadBelt;
 
 /// Performance panel for displaying FPS, frame times, and profiling data
 pub struct PerformancePanel {
@@ -161,6 +163,11 @@ impl PerformancePanel {
         ui.add_space(15.0);
         ui.separator();
 
+        self.render_upload_stats(ui);
+
+        ui.add_space(15.0);
+        ui.separator();
+
         // Graph controls
         ui.heading("Performance Graphs");
         ui.add_space(5.0);
@@ -196,6 +203,67 @@ impl PerformancePanel {
         ui.label("• Use GPU profiling tools for detailed analysis");
     }
 
+    /// Render the upload belt counters
+    fn render_upload_stats(&self, ui: &mut egui::Ui) {
+        let stats = UploadBelt::global().stats();
+
+        ui.heading("Uploads");
+        ui.add_space(5.0);
+        ui.label(
+            "Per-frame buffer updates from the previews are staged in a ring of reusable \
+             staging buffers, recycled once the GPU has finished copying from them.",
+        );
+        ui.add_space(5.0);
+
+        egui::Grid::new("upload_stats")
+            .num_columns(2)
+            .spacing([40.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Uploaded Last Frame:");
+                ui.label(format!(
+                    "{} in {} writes",
+                    format_bytes(stats.frame_bytes),
+                    stats.frame_writes
+                ));
+                ui.end_row();
+
+                ui.label("Uploaded Total:");
+                ui.label(format!(
+                    "{} in {} writes",
+                    format_bytes(stats.total_bytes),
+                    stats.total_writes
+                ));
+                ui.end_row();
+
+                ui.label("Staging Chunks:");
+                ui.label(format!(
+                    "{} ({} in flight, {} free, {})",
+                    stats.chunks_allocated,
+                    stats.chunks_in_flight,
+                    stats.chunks_free,
+                    format_bytes(stats.staging_bytes)
+                ));
+                ui.end_row();
+
+                ui.label("Chunks Reused:");
+                ui.label(format!("{}", stats.chunks_reused));
+                ui.end_row();
+
+                ui.label("Stalls Avoided:");
+                ui.label(format!("{}", stats.stalls_avoided)).on_hover_text(
+                    "Writes made while earlier staging buffers were still being read \
+                         by the GPU",
+                );
+                ui.end_row();
+
+                ui.label("Direct Writes:");
+                ui.label(format!("{}", stats.fallback_writes))
+                    .on_hover_text("Writes for devices the belt is not open for");
+                ui.end_row();
+            });
+    }
+
     /// Render frame time graph
     fn render_frame_time_graph(&self, ui: &mut egui::Ui) {
         use egui_plot::{Line, Plot, PlotPoints};
@@ -0,0 +1,292 @@
+//! Compact text permalinks of a shader and pipeline setup
+//!
+//! A permalink is [`PREFIX`] followed by the deflate-compressed JSON of a
+//! [`PlaygroundState`], encoded as URL-safe base64. It is short enough to
+//! paste into a chat message or an issue, and can be imported either as is
+//! or inside a link carrying it in a `permalink=` query parameter.
+//!
+//! Only the shader and the pipeline configuration are shared, see
+//! [`shareable_state`]; buffers, textures, settings and the shader's version
+//! history stay local.
+
+use crate::state::PlaygroundState;
+use base64::prelude::*;
+use std::fmt;
+
+/// Marks a permalink and the version of its format
+pub const PREFIX: &str = "wgpup1.";
+
+/// Query parameter carrying a permalink in a URL
+pub const QUERY_PARAMETER: &str = "permalink";
+
+/// Largest decompressed state accepted when importing
+pub const MAX_DECODED_BYTES: usize = 4 * 1024 * 1024;
+
+/// Deflate level used for encoding, the best compression
+const COMPRESSION_LEVEL: u8 = 10;
+
+/// Errors of [`encode`] and [`decode`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermalinkError {
+    /// The text does not contain a permalink
+    NotAPermalink,
+    /// The permalink comes from a newer version of the playground
+    UnsupportedVersion(String),
+    InvalidBase64(String),
+    /// The compressed data is corrupt, e.g. because the text was cut off
+    InvalidData(String),
+    /// The decompressed state is larger than [`MAX_DECODED_BYTES`]
+    TooLarge,
+    InvalidState(String),
+}
+
+impl fmt::Display for PermalinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PermalinkError::NotAPermalink => write!(
+                f,
+                "Not a playground permalink, expected text starting with '{}'",
+                PREFIX
+            ),
+            PermalinkError::UnsupportedVersion(prefix) => write!(
+                f,
+                "Permalink format '{}' is not supported by this version of the playground",
+                prefix
+            ),
+            PermalinkError::InvalidBase64(msg) => write!(f, "Invalid permalink encoding: {}", msg),
+            PermalinkError::InvalidData(msg) => {
+                write!(f, "Permalink data is corrupt or incomplete: {}", msg)
+            }
+            PermalinkError::TooLarge => write!(
+                f,
+                "Permalink expands to more than {} bytes",
+                MAX_DECODED_BYTES
+            ),
+            PermalinkError::InvalidState(msg) => write!(f, "Invalid permalink state: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PermalinkError {}
+
+/// The part of `state` shared through a permalink
+///
+/// Keeps the shader editor (without its version history), the render and
+/// compute pipelines and the bind group layout they are built with.
+pub fn shareable_state(state: &PlaygroundState) -> PlaygroundState {
+    PlaygroundState {
+        version: state.version.clone(),
+        shader_editor: state.shader_editor.clone().map(|mut shader| {
+            shader.history = Default::default();
+            shader
+        }),
+        render_pipeline_panel: state.render_pipeline_panel.clone(),
+        compute_pipeline_panel: state.compute_pipeline_panel.clone(),
+        bind_group_layout_panel: state.bind_group_layout_panel.clone(),
+        ..Default::default()
+    }
+}
+
+/// Encode the shareable part of `state` as a permalink
+pub fn encode(state: &PlaygroundState) -> Result<String, PermalinkError> {
+    let json = serde_json::to_vec(&shareable_state(state))
+        .map_err(|e| PermalinkError::InvalidState(e.to_string()))?;
+    let compressed = miniz_oxide::deflate::compress_to_vec(&json, COMPRESSION_LEVEL);
+    Ok(format!(
+        "{}{}",
+        PREFIX,
+        BASE64_URL_SAFE_NO_PAD.encode(compressed)
+    ))
+}
+
+/// Decode a permalink, given alone or inside a link or surrounding text
+///
+/// The returned state only has the fields set that [`shareable_state`]
+/// keeps, so importing it leaves the rest of the playground untouched.
+pub fn decode(text: &str) -> Result<PlaygroundState, PermalinkError> {
+    let payload = find_payload(text)?;
+    let compressed = BASE64_URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|e| PermalinkError::InvalidBase64(e.to_string()))?;
+    let json = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_DECODED_BYTES)
+        .map_err(|e| match e.status {
+        miniz_oxide::inflate::TINFLStatus::HasMoreOutput => PermalinkError::TooLarge,
+        _ => PermalinkError::InvalidData(e.to_string()),
+    })?;
+    let state: PlaygroundState =
+        serde_json::from_slice(&json).map_err(|e| PermalinkError::InvalidState(e.to_string()))?;
+    Ok(shareable_state(&state))
+}
+
+/// Whether `text` looks like it contains a permalink, e.g. on the clipboard
+pub fn contains_permalink(text: &str) -> bool {
+    find_payload(text).is_ok()
+}
+
+/// The base64 payload of the permalink in `text`
+fn find_payload(text: &str) -> Result<&str, PermalinkError> {
+    let text = text.trim();
+    // A link: take the value of the query parameter
+    let text = text
+        .split(['?', '&', '#'])
+        .find_map(|part| part.strip_prefix(QUERY_PARAMETER)?.strip_prefix('='))
+        .unwrap_or(text);
+
+    let start = text.find(PREFIX).ok_or_else(|| {
+        // Same marker with another version number
+        let base = PREFIX.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        match text.find(base) {
+            Some(start) => {
+                let prefix: String = text[start..]
+                    .chars()
+                    .take_while(|c| *c != '.' && !c.is_whitespace())
+                    .collect();
+                PermalinkError::UnsupportedVersion(prefix)
+            }
+            None => PermalinkError::NotAPermalink,
+        }
+    })?;
+    let payload = &text[start + PREFIX.len()..];
+    let end = payload
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(payload.len());
+    match &payload[..end] {
+        "" => Err(PermalinkError::InvalidData(
+            "the permalink is empty".to_string(),
+        )),
+        payload => Ok(payload),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{BufferPanelState, RenderPipelinePanelState, ShaderEditorState, Theme};
+
+    fn setup() -> PlaygroundState {
+        let mut shader = ShaderEditorState {
+            source_code: "@fragment\nfn fs_main() -> @location(0) vec4<f32> {\n    \
+                          return vec4<f32>(1.0, 0.5, 0.0, 1.0);\n}\n"
+                .repeat(8),
+            label: "shared".to_string(),
+            file_path: "shared.wgsl".to_string(),
+            ..Default::default()
+        };
+        shader.history.record("shared.wgsl", "fn old() {}");
+        PlaygroundState {
+            theme: Theme::Light,
+            shader_editor: Some(shader),
+            render_pipeline_panel: Some(RenderPipelinePanelState {
+                label: "pipeline".to_string(),
+                vertex_entry_point: "vs_main".to_string(),
+                fragment_entry_point: "fs_main".to_string(),
+                cull_mode: "Back".to_string(),
+                ..Default::default()
+            }),
+            buffer_panel: Some(BufferPanelState {
+                label: "local".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_keeps_shader_and_pipeline() {
+        let state = setup();
+        let link = encode(&state).unwrap();
+        assert!(link.starts_with(PREFIX));
+        assert!(link[PREFIX.len()..]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let decoded = decode(&link).unwrap();
+        let shader = decoded.shader_editor.as_ref().unwrap();
+        let original = state.shader_editor.as_ref().unwrap();
+        assert_eq!(shader.source_code, original.source_code);
+        assert_eq!(shader.file_path, "shared.wgsl");
+        assert!(shader.history.is_empty());
+        let pipeline = decoded.render_pipeline_panel.as_ref().unwrap();
+        assert_eq!(pipeline.cull_mode, "Back");
+        assert_eq!(pipeline.fragment_entry_point, "fs_main");
+
+        // Local parts are left out
+        assert!(decoded.buffer_panel.is_none());
+        assert_eq!(decoded.theme, Theme::default());
+    }
+
+    #[test]
+    fn test_permalink_is_compressed() {
+        let state = shareable_state(&setup());
+        let json = serde_json::to_string(&state).unwrap();
+        let link = encode(&state).unwrap();
+        assert!(
+            link.len() * 2 < json.len(),
+            "{} vs {}",
+            link.len(),
+            json.len()
+        );
+    }
+
+    #[test]
+    fn test_decode_finds_permalink_in_text_and_links() {
+        let link = encode(&setup()).unwrap();
+        let expected = decode(&link).unwrap().shader_editor.unwrap().source_code;
+
+        for text in [
+            format!("  {}\n", link),
+            format!("Try this: {} (wrong cull mode?)", link),
+            format!("https://example.com/demo?{}={}", QUERY_PARAMETER, link),
+            format!("https://example.com/?a=1&{}={}#top", QUERY_PARAMETER, link),
+        ] {
+            assert!(contains_permalink(&text), "{}", text);
+            let decoded = decode(&text).unwrap();
+            assert_eq!(decoded.shader_editor.unwrap().source_code, expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            decode("hello"),
+            Err(PermalinkError::NotAPermalink)
+        ));
+        assert!(!contains_permalink("hello"));
+        assert!(matches!(
+            decode("wgpup2.AAAA"),
+            Err(PermalinkError::UnsupportedVersion(prefix)) if prefix == "wgpup2"
+        ));
+        assert!(matches!(
+            decode(PREFIX),
+            Err(PermalinkError::InvalidData(_))
+        ));
+        assert!(matches!(
+            decode("wgpup1.A"),
+            Err(PermalinkError::InvalidBase64(_))
+        ));
+
+        // A permalink cut off while copying
+        let link = encode(&setup()).unwrap();
+        let cut = PREFIX.len() + (link.len() - PREFIX.len()) / 8 * 4;
+        assert!(matches!(
+            decode(&link[..cut]),
+            Err(PermalinkError::InvalidData(_))
+        ));
+
+        // Valid compression of something that isn't a state
+        let not_state = miniz_oxide::deflate::compress_to_vec(b"[1, 2, 3]", 6);
+        let text = format!("{}{}", PREFIX, BASE64_URL_SAFE_NO_PAD.encode(not_state));
+        assert!(matches!(
+            decode(&text),
+            Err(PermalinkError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_rejects_oversized_state() {
+        let huge = vec![b' '; MAX_DECODED_BYTES + 1];
+        let compressed = miniz_oxide::deflate::compress_to_vec(&huge, 6);
+        let text = format!("{}{}", PREFIX, BASE64_URL_SAFE_NO_PAD.encode(compressed));
+        assert!(matches!(decode(&text), Err(PermalinkError::TooLarge)));
+    }
+}
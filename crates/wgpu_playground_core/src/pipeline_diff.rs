@@ -0,0 +1,393 @@
+//! Field-by-field comparison of render pipeline descriptors
+//!
+//! Flattens a [`RenderPipelineDescriptor`] into labelled fields so two
+//! configurations can be compared in a table, e.g. a snapshotted baseline
+//! against the settings currently being edited.
+
+use crate::render_pipeline::{
+    BlendComponent, ColorTargetState, ColorWrites, DepthStencilState, RenderPipelineDescriptor,
+    StencilFaceState,
+};
+
+/// Value shown for fields that don't exist in one of the descriptors
+pub const ABSENT: &str = "—";
+
+/// One flattened descriptor field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorField {
+    /// Descriptor section, e.g. "Primitive"
+    pub section: &'static str,
+    /// Field name within the section, e.g. "cull_mode"
+    pub field: String,
+    pub value: String,
+}
+
+/// A field compared between the baseline and the current descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub section: &'static str,
+    pub field: String,
+    pub baseline: String,
+    pub current: String,
+}
+
+impl FieldDiff {
+    /// Whether the value differs between the two descriptors
+    pub fn is_changed(&self) -> bool {
+        self.baseline != self.current
+    }
+}
+
+/// Result of [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DescriptorDiff {
+    /// All fields of both descriptors, in descriptor order
+    pub fields: Vec<FieldDiff>,
+}
+
+impl DescriptorDiff {
+    /// Only the fields that differ
+    pub fn changed(&self) -> impl Iterator<Item = &FieldDiff> {
+        self.fields.iter().filter(|f| f.is_changed())
+    }
+
+    pub fn changed_count(&self) -> usize {
+        self.changed().count()
+    }
+
+    pub fn is_identical(&self) -> bool {
+        self.changed_count() == 0
+    }
+
+    /// Sections containing at least one changed field, in order
+    pub fn changed_sections(&self) -> Vec<&'static str> {
+        let mut sections = Vec::new();
+        for field in self.changed() {
+            if !sections.contains(&field.section) {
+                sections.push(field.section);
+            }
+        }
+        sections
+    }
+}
+
+/// Collects fields, tagging each with the current section
+struct Fields {
+    section: &'static str,
+    fields: Vec<DescriptorField>,
+}
+
+impl Fields {
+    fn section(&mut self, section: &'static str) -> &mut Self {
+        self.section = section;
+        self
+    }
+
+    fn push(&mut self, field: impl Into<String>, value: impl ToString) {
+        self.fields.push(DescriptorField {
+            section: self.section,
+            field: field.into(),
+            value: value.to_string(),
+        });
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "Yes"
+    } else {
+        "No"
+    }
+}
+
+fn write_mask_name(mask: ColorWrites) -> String {
+    let mask = mask.to_wgpu();
+    [
+        (wgpu::ColorWrites::RED, 'R'),
+        (wgpu::ColorWrites::GREEN, 'G'),
+        (wgpu::ColorWrites::BLUE, 'B'),
+        (wgpu::ColorWrites::ALPHA, 'A'),
+    ]
+    .iter()
+    .map(|(bit, c)| if mask.contains(*bit) { *c } else { '-' })
+    .collect()
+}
+
+fn blend_component_name(component: &BlendComponent) -> String {
+    format!(
+        "{:?} × src {:?} dst {:?}",
+        component.operation, component.src_factor, component.dst_factor
+    )
+}
+
+fn push_stencil_face(fields: &mut Fields, face: &str, state: &StencilFaceState) {
+    fields.push(
+        format!("stencil_{}.compare", face),
+        format!("{:?}", state.compare),
+    );
+    fields.push(
+        format!("stencil_{}.fail_op", face),
+        format!("{:?}", state.fail_op),
+    );
+    fields.push(
+        format!("stencil_{}.depth_fail_op", face),
+        format!("{:?}", state.depth_fail_op),
+    );
+    fields.push(
+        format!("stencil_{}.pass_op", face),
+        format!("{:?}", state.pass_op),
+    );
+}
+
+fn push_depth_stencil(fields: &mut Fields, state: Option<&DepthStencilState>) {
+    fields.push("enabled", yes_no(state.is_some()));
+    // Disabled depth-stencil state has no further fields, which shows up as
+    // absent values when compared with an enabled one.
+    let Some(state) = state else {
+        return;
+    };
+    fields.push("format", format!("{:?}", state.format));
+    fields.push("depth_write_enabled", yes_no(state.depth_write_enabled));
+    fields.push("depth_compare", format!("{:?}", state.depth_compare));
+    push_stencil_face(fields, "front", &state.stencil_front);
+    push_stencil_face(fields, "back", &state.stencil_back);
+    fields.push(
+        "stencil_read_mask",
+        format!("0x{:08X}", state.stencil_read_mask),
+    );
+    fields.push(
+        "stencil_write_mask",
+        format!("0x{:08X}", state.stencil_write_mask),
+    );
+    fields.push("depth_bias", state.depth_bias);
+    fields.push("depth_bias_slope_scale", state.depth_bias_slope_scale);
+    fields.push("depth_bias_clamp", state.depth_bias_clamp);
+}
+
+fn push_target(fields: &mut Fields, index: usize, target: &ColorTargetState) {
+    fields.push(
+        format!("targets[{}].format", index),
+        format!("{:?}", target.format),
+    );
+    fields.push(
+        format!("targets[{}].blend", index),
+        yes_no(target.blend.is_some()),
+    );
+    if let Some(blend) = &target.blend {
+        fields.push(
+            format!("targets[{}].blend.color", index),
+            blend_component_name(&blend.color),
+        );
+        fields.push(
+            format!("targets[{}].blend.alpha", index),
+            blend_component_name(&blend.alpha),
+        );
+    }
+    fields.push(
+        format!("targets[{}].write_mask", index),
+        write_mask_name(target.write_mask),
+    );
+}
+
+/// Flatten a descriptor into labelled fields
+pub fn describe(descriptor: &RenderPipelineDescriptor) -> Vec<DescriptorField> {
+    let mut fields = Fields {
+        section: "Pipeline",
+        fields: Vec::new(),
+    };
+
+    fields.push("label", descriptor.label().unwrap_or(ABSENT));
+
+    fields
+        .section("Vertex")
+        .push("entry_point", descriptor.vertex_entry_point());
+    fields.push("buffers", descriptor.vertex_buffers().len());
+    for (i, layout) in descriptor.vertex_buffers().iter().enumerate() {
+        fields.push(format!("buffers[{}].array_stride", i), layout.array_stride);
+        fields.push(
+            format!("buffers[{}].step_mode", i),
+            format!("{:?}", layout.step_mode),
+        );
+        for attribute in &layout.attributes {
+            fields.push(
+                format!("buffers[{}].location({})", i, attribute.shader_location),
+                format!("{:?} @ {}", attribute.format, attribute.offset),
+            );
+        }
+    }
+
+    let primitive = descriptor.primitive();
+    fields
+        .section("Primitive")
+        .push("topology", format!("{:?}", primitive.topology));
+    fields.push("cull_mode", format!("{:?}", primitive.cull_mode));
+    fields.push("front_face", format!("{:?}", primitive.front_face));
+    fields.push("polygon_mode", format!("{:?}", primitive.polygon_mode));
+
+    push_depth_stencil(fields.section("Depth-Stencil"), descriptor.depth_stencil());
+
+    let multisample = descriptor.multisample();
+    fields
+        .section("Multisample")
+        .push("count", multisample.count);
+    fields.push("mask", format!("0x{:X}", multisample.mask));
+    fields.push(
+        "alpha_to_coverage_enabled",
+        yes_no(multisample.alpha_to_coverage_enabled),
+    );
+
+    fields
+        .section("Fragment")
+        .push("entry_point", descriptor.fragment_entry_point());
+    fields.push("targets", descriptor.fragment_targets().len());
+    for (i, target) in descriptor.fragment_targets().iter().enumerate() {
+        push_target(&mut fields, i, target);
+    }
+
+    fields.fields
+}
+
+/// Compare two descriptors field by field
+///
+/// Fields present in only one descriptor are reported with [`ABSENT`] on
+/// the other side. Order follows the baseline, with fields that only exist
+/// in `current` inserted after their predecessor.
+pub fn diff(
+    baseline: &RenderPipelineDescriptor,
+    current: &RenderPipelineDescriptor,
+) -> DescriptorDiff {
+    let baseline_fields = describe(baseline);
+    let current_fields = describe(current);
+    let key = |f: &DescriptorField| (f.section, f.field.clone());
+
+    let mut fields: Vec<FieldDiff> = baseline_fields
+        .iter()
+        .map(|b| FieldDiff {
+            section: b.section,
+            field: b.field.clone(),
+            baseline: b.value.clone(),
+            current: current_fields
+                .iter()
+                .find(|c| key(c) == key(b))
+                .map_or_else(|| ABSENT.to_string(), |c| c.value.clone()),
+        })
+        .collect();
+
+    let mut insert_at = 0;
+    for c in &current_fields {
+        match fields
+            .iter()
+            .position(|f| f.section == c.section && f.field == c.field)
+        {
+            Some(position) => insert_at = position + 1,
+            None => {
+                fields.insert(
+                    insert_at,
+                    FieldDiff {
+                        section: c.section,
+                        field: c.field.clone(),
+                        baseline: ABSENT.to_string(),
+                        current: c.value.clone(),
+                    },
+                );
+                insert_at += 1;
+            }
+        }
+    }
+
+    DescriptorDiff { fields }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_pipeline::{
+        BlendState, CompareFunction, CullMode, PrimitiveState, PrimitiveTopology,
+    };
+
+    fn descriptor() -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor::new(Some("test"))
+            .with_vertex_entry_point("vs_main")
+            .with_fragment_entry_point("fs_main")
+            .with_fragment_target(ColorTargetState::new(wgpu::TextureFormat::Bgra8UnormSrgb))
+    }
+
+    #[test]
+    fn test_identical_descriptors() {
+        let diff = diff(&descriptor(), &descriptor());
+        assert!(diff.is_identical());
+        assert!(!diff.fields.is_empty());
+        assert!(diff.changed_sections().is_empty());
+    }
+
+    #[test]
+    fn test_changed_field() {
+        let current = descriptor().with_primitive(
+            PrimitiveState::new()
+                .with_topology(PrimitiveTopology::LineList)
+                .with_cull_mode(CullMode::Back),
+        );
+        let diff = diff(&descriptor(), &current);
+        let changed: Vec<_> = diff.changed().map(|f| f.field.as_str()).collect();
+        assert_eq!(changed, vec!["topology", "cull_mode"]);
+        assert_eq!(diff.changed_sections(), vec!["Primitive"]);
+
+        let topology = diff.changed().next().unwrap();
+        assert_eq!(topology.baseline, "TriangleList");
+        assert_eq!(topology.current, "LineList");
+    }
+
+    #[test]
+    fn test_added_section_is_absent_in_baseline() {
+        let current = descriptor().with_depth_stencil(
+            DepthStencilState::new(wgpu::TextureFormat::Depth24Plus)
+                .with_depth_compare(CompareFunction::LessEqual),
+        );
+        let diff = diff(&descriptor(), &current);
+
+        let enabled = diff.fields.iter().find(|f| f.field == "enabled").unwrap();
+        assert_eq!(
+            (enabled.baseline.as_str(), enabled.current.as_str()),
+            ("No", "Yes")
+        );
+
+        let compare = diff
+            .fields
+            .iter()
+            .find(|f| f.field == "depth_compare")
+            .unwrap();
+        assert_eq!(compare.baseline, ABSENT);
+        assert_eq!(compare.current, "LessEqual");
+
+        // New fields are placed with their section, before multisample state
+        let position = |name: &str| diff.fields.iter().position(|f| f.field == name).unwrap();
+        assert!(position("enabled") < position("depth_compare"));
+        assert!(position("depth_compare") < position("count"));
+    }
+
+    #[test]
+    fn test_blend_and_write_mask() {
+        let current = RenderPipelineDescriptor::new(Some("test"))
+            .with_vertex_entry_point("vs_main")
+            .with_fragment_entry_point("fs_main")
+            .with_fragment_target(
+                ColorTargetState::new(wgpu::TextureFormat::Bgra8UnormSrgb)
+                    .with_blend(BlendState::alpha_blending())
+                    .with_write_mask(ColorWrites::RED | ColorWrites::ALPHA),
+            );
+        let diff = diff(&descriptor(), &current);
+
+        let mask = diff
+            .fields
+            .iter()
+            .find(|f| f.field == "targets[0].write_mask")
+            .unwrap();
+        assert_eq!(
+            (mask.baseline.as_str(), mask.current.as_str()),
+            ("RGBA", "R--A")
+        );
+        assert!(diff
+            .changed()
+            .any(|f| f.field == "targets[0].blend.color" && f.baseline == ABSENT));
+    }
+}
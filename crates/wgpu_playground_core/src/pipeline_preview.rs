@@ -1,16 +1,28 @@
 /// Pipeline preview rendering for the Render Pipeline panel
 ///
 /// Provides live visualization of pipeline configurations:
-/// - Wireframe mode for topology changes
+/// - Wireframe and point polygon modes, and topology changes
 /// - Culling visualization
 /// - Blend mode demonstration
 /// - Depth testing effect
+///
+/// The preview draws every mesh node of a [`Scene`], a single cube unless
+/// the scene has been edited. Nodes may also use generated
+/// [`Primitive`]s, whose buffers are created when a node first needs them.
 use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
-use crate::math_utils::{cross, dot, normalize};
+use crate::camera::Camera;
+use crate::geometry::Primitive;
+use crate::math_utils::{mat4_mul, rotation_x, rotation_y, MAT4_IDENTITY};
 use crate::render_pipeline::{
-    BlendState, CompareFunction, CullMode, DepthStencilState, FrontFace, MultisampleState,
-    PrimitiveState, PrimitiveTopology,
+    BlendState, ColorTargetState, DepthStencilState, MultisampleState, PipelineCache, PolygonMode,
+    PrimitiveState, RenderPipelineDescriptor, VertexAttribute, VertexBufferLayout, VertexFormat,
+    VertexStepMode,
 };
+use crate::scene::{DrawItem, MeshRef, Scene};
+use crate::shader::ShaderModule;
+use crate::upload_belt;
+use std::ops::Range;
+use std::sync::Arc;
 use wgpu::util::DeviceExt;
 
 /// Vertex structure for preview rendering
@@ -21,18 +33,187 @@ struct PreviewVertex {
     color: [f32; 3],
 }
 
+/// Per-node uniforms, one aligned slot each in the frame's uniform buffer
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct PreviewUniforms {
+    mvp: [[f32; 4]; 4],
+    tint: [f32; 4],
+}
+
+const UNIFORMS_SIZE: u64 = std::mem::size_of::<PreviewUniforms>() as u64;
+
+/// Uniform slots allocated when the preview is set up; the buffer grows
+/// for scenes with more nodes
+const INITIAL_UNIFORM_SLOTS: u64 = 16;
+
+/// Indices of the cube, followed by those of the plane
+const CUBE_INDICES: Range<u32> = 0..36;
+const PLANE_INDICES: Range<u32> = 36..42;
+
+/// Range of the shared index buffer; primitives have buffers of their own
+fn mesh_indices(mesh: MeshRef) -> Range<u32> {
+    match mesh {
+        MeshRef::Cube => CUBE_INDICES,
+        MeshRef::Plane => PLANE_INDICES,
+        MeshRef::Primitive(_) => 0..0,
+    }
+}
+
+/// Buffers of a generated primitive
+struct PrimitiveMesh {
+    vertex_buffer: wgpu::Buffer,
+    /// 32-bit indices; fine primitives exceed the shared buffer's 16 bits
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+}
+
+impl PrimitiveMesh {
+    fn new(device: &wgpu::Device, primitive: Primitive) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        let geometry = primitive.generate();
+        // Colored by normal, so facets read like the cube's faces
+        let vertices: Vec<PreviewVertex> = geometry
+            .vertices
+            .iter()
+            .map(|vertex| PreviewVertex {
+                position: vertex.position,
+                color: vertex.normal.map(|c| 0.65 + 0.35 * c),
+            })
+            .collect();
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Pipeline Preview Primitive Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Pipeline Preview Primitive Index Buffer"),
+            contents: bytemuck::cast_slice(&geometry.indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        Self {
+            vertex_buffer,
+            index_buffer,
+            index_count: geometry.indices.len() as u32,
+        }
+    }
+}
+
+/// Shader drawing the preview meshes with per-node uniforms
+const PREVIEW_SHADER: &str = r#"
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+struct Uniforms {
+    mvp: mat4x4<f32>,
+    tint: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+    output.position = uniforms.mvp * vec4<f32>(input.position, 1.0);
+    output.color = vec4<f32>(input.color, 1.0) * uniforms.tint;
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    return input.color;
+}
+"#;
+
+/// The pipeline the preview draws with for a configuration
+fn preview_descriptor(
+    device: &wgpu::Device,
+    primitive: &PrimitiveState,
+    depth_stencil: Option<&DepthStencilState>,
+    blend: Option<&BlendState>,
+    multisample: &MultisampleState,
+) -> RenderPipelineDescriptor {
+    // A mode the device lacks the feature for would fail pipeline
+    // creation, e.g. after importing a state saved on another machine
+    let mut primitive = *primitive;
+    if !primitive.polygon_mode.is_supported(device.features()) {
+        primitive.polygon_mode = PolygonMode::Fill;
+    }
+
+    let mut target = ColorTargetState::new(wgpu::TextureFormat::Rgba8UnormSrgb);
+    target.blend = blend.copied();
+
+    let mut descriptor = RenderPipelineDescriptor::new(Some("Pipeline Preview Pipeline"))
+        .with_vertex_buffer(
+            VertexBufferLayout::new(
+                std::mem::size_of::<PreviewVertex>() as u64,
+                VertexStepMode::Vertex,
+            )
+            .with_attribute(VertexAttribute::new(0, VertexFormat::Float32x3, 0))
+            .with_attribute(VertexAttribute::new(
+                1,
+                VertexFormat::Float32x3,
+                std::mem::size_of::<[f32; 3]>() as u64,
+            )),
+        )
+        .with_primitive(primitive)
+        .with_multisample(
+            MultisampleState::new()
+                .with_count(multisample.count)
+                .with_alpha_to_coverage(multisample.alpha_to_coverage_enabled),
+        )
+        .with_fragment_target(target)
+        .with_vertex_entry_point("vs_main")
+        .with_fragment_entry_point("fs_main");
+    if let Some(ds) = depth_stencil {
+        descriptor = descriptor.with_depth_stencil(
+            DepthStencilState::new(wgpu::TextureFormat::Depth24Plus)
+                .with_depth_write_enabled(ds.depth_write_enabled)
+                .with_depth_compare(ds.depth_compare),
+        );
+    }
+    descriptor
+}
+
+/// Distance between uniform slots, honoring the device's offset alignment
+fn uniform_stride(device: &wgpu::Device) -> u64 {
+    UNIFORMS_SIZE.next_multiple_of(u64::from(
+        device.limits().min_uniform_buffer_offset_alignment,
+    ))
+}
+
 /// State for pipeline preview rendering
 pub struct RenderPipelinePreviewState {
-    /// The render pipeline for preview
-    pipeline: Option<wgpu::RenderPipeline>,
+    /// Shader with both preview entry points
+    shader: ShaderModule,
+    /// The render pipeline for preview, shared with the pipeline cache
+    pipeline: Option<Arc<wgpu::RenderPipeline>>,
     /// Bind group layout for uniforms
     bind_group_layout: Option<wgpu::BindGroupLayout>,
-    /// Preview vertex buffer (cube mesh)
+    /// Pipeline layout of every preview pipeline; keeps cache keys stable
+    pipeline_layout: Option<wgpu::PipelineLayout>,
+    /// Per-node uniform slots, rewritten every frame through the upload belt
+    uniform_buffer: Option<wgpu::Buffer>,
+    /// Bind group of `uniform_buffer`
+    uniform_bind_group: Option<wgpu::BindGroup>,
+    /// Preview vertex buffer (cube and plane meshes)
     vertex_buffer: Option<wgpu::Buffer>,
     /// Preview index buffer
     index_buffer: Option<wgpu::Buffer>,
-    /// Number of indices to draw
-    index_count: u32,
+    /// Buffers of the primitives the scene draws, by parameters
+    primitives: Vec<(Primitive, PrimitiveMesh)>,
     /// Render texture for preview
     render_texture: Option<wgpu::Texture>,
     /// Render texture view
@@ -60,11 +241,16 @@ impl Default for RenderPipelinePreviewState {
 impl RenderPipelinePreviewState {
     pub fn new() -> Self {
         Self {
+            shader: ShaderModule::from_source(PREVIEW_SHADER, Some("Pipeline Preview Shader"))
+                .expect("preview shader source is not empty"),
             pipeline: None,
             bind_group_layout: None,
+            pipeline_layout: None,
+            uniform_buffer: None,
+            uniform_bind_group: None,
             vertex_buffer: None,
             index_buffer: None,
-            index_count: 0,
+            primitives: Vec::new(),
             render_texture: None,
             render_texture_view: None,
             depth_texture: None,
@@ -76,13 +262,64 @@ impl RenderPipelinePreviewState {
         }
     }
 
+    /// The pipeline the preview currently draws with
+    pub fn pipeline(&self) -> Option<&Arc<wgpu::RenderPipeline>> {
+        self.pipeline.as_ref()
+    }
+
     /// Initialize rendering resources
     pub fn initialize(&mut self, device: &wgpu::Device) {
         self.init_render_texture(device);
         self.init_depth_texture(device);
+        self.init_layout(device);
+        self.uniform_buffer = None;
+        self.uniform_bind_group = None;
+        self.reserve_uniforms(device, uniform_stride(device) * INITIAL_UNIFORM_SLOTS);
         self.init_geometry(device);
     }
 
+    /// Make sure the uniform buffer holds at least `size` bytes, recreating
+    /// it and its bind group if not
+    fn reserve_uniforms(&mut self, device: &wgpu::Device, size: u64) {
+        if self.uniform_bind_group.is_some()
+            && self
+                .uniform_buffer
+                .as_ref()
+                .is_some_and(|buffer| buffer.size() >= size)
+        {
+            return;
+        }
+        let Some(bind_group_layout) = &self.bind_group_layout else {
+            return;
+        };
+        let tracker = ApiCoverageTracker::global();
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Pipeline Preview Uniform Buffer"),
+            size,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Pipeline Preview Bind Group"),
+            layout: bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &buffer,
+                    offset: 0,
+                    size: wgpu::BufferSize::new(UNIFORMS_SIZE),
+                }),
+            }],
+        });
+
+        self.uniform_buffer = Some(buffer);
+        self.uniform_bind_group = Some(bind_group);
+    }
+
     /// Initialize render texture
     fn init_render_texture(&mut self, device: &wgpu::Device) {
         let tracker = ApiCoverageTracker::global();
@@ -139,7 +376,38 @@ impl RenderPipelinePreviewState {
         self.depth_texture_view = Some(view);
     }
 
-    /// Initialize cube geometry for preview
+    /// Initialize the uniform bind group layout and the pipeline layout,
+    /// which every preview pipeline shares
+    fn init_layout(&mut self, device: &wgpu::Device) {
+        let tracker = ApiCoverageTracker::global();
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Pipeline Preview Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: wgpu::BufferSize::new(UNIFORMS_SIZE),
+                },
+                count: None,
+            }],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Pipeline Preview Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        self.bind_group_layout = Some(bind_group_layout);
+        self.pipeline_layout = Some(pipeline_layout);
+    }
+
+    /// Initialize cube and plane geometry for preview
     fn init_geometry(&mut self, device: &wgpu::Device) {
         let tracker = ApiCoverageTracker::global();
 
@@ -248,6 +516,23 @@ impl RenderPipelinePreviewState {
                 position: [-0.5, 0.5, -0.5],
                 color: [0.3, 1.0, 1.0],
             },
+            // Plane (gray)
+            PreviewVertex {
+                position: [-0.5, 0.0, 0.5],
+                color: [0.6, 0.6, 0.6],
+            },
+            PreviewVertex {
+                position: [0.5, 0.0, 0.5],
+                color: [0.6, 0.6, 0.6],
+            },
+            PreviewVertex {
+                position: [0.5, 0.0, -0.5],
+                color: [0.6, 0.6, 0.6],
+            },
+            PreviewVertex {
+                position: [-0.5, 0.0, -0.5],
+                color: [0.6, 0.6, 0.6],
+            },
         ];
 
         let indices: Vec<u16> = vec![
@@ -257,10 +542,10 @@ impl RenderPipelinePreviewState {
             8, 9, 10, 8, 10, 11, // Bottom face
             12, 13, 14, 12, 14, 15, // Right face
             16, 17, 18, 16, 18, 19, // Left face
-            20, 21, 22, 20, 22, 23,
+            20, 21, 22, 20, 22, 23, // Plane
+            24, 25, 26, 24, 26, 27,
         ];
-
-        self.index_count = indices.len() as u32;
+        debug_assert_eq!(indices.len() as u32, PLANE_INDICES.end);
 
         tracker.record(ApiCategory::Buffer, "create_buffer");
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -280,276 +565,104 @@ impl RenderPipelinePreviewState {
         self.index_buffer = Some(index_buffer);
     }
 
-    /// Create or recreate the pipeline with the specified configuration
+    /// Create buffers for the primitives `draws` uses and drop the others,
+    /// so dragging a parameter slider doesn't pile up meshes
+    fn update_primitives(&mut self, device: &wgpu::Device, draws: &[DrawItem]) {
+        let used: Vec<Primitive> = draws
+            .iter()
+            .filter_map(|draw| match draw.mesh {
+                MeshRef::Primitive(primitive) => Some(primitive),
+                _ => None,
+            })
+            .collect();
+        self.primitives
+            .retain(|(primitive, _)| used.contains(primitive));
+        for primitive in used {
+            if !self.primitives.iter().any(|(p, _)| *p == primitive) {
+                let mesh = PrimitiveMesh::new(device, primitive);
+                self.primitives.push((primitive, mesh));
+            }
+        }
+    }
+
+    /// Switch to the pipeline for the specified configuration, creating it
+    /// only if `cache` does not hold it yet
+    ///
+    /// Stencil and depth bias settings are not previewed, and a polygon mode
+    /// the device lacks the feature for falls back to fill.
     pub fn update_pipeline(
         &mut self,
         device: &wgpu::Device,
+        cache: &PipelineCache,
         primitive: &PrimitiveState,
         depth_stencil: Option<&DepthStencilState>,
         blend: Option<&BlendState>,
         multisample: &MultisampleState,
     ) {
-        // Create shader for pipeline preview
-        let shader_source = r#"
-struct VertexInput {
-    @location(0) position: vec3<f32>,
-    @location(1) color: vec3<f32>,
-}
-
-struct VertexOutput {
-    @builtin(position) position: vec4<f32>,
-    @location(0) color: vec3<f32>,
-}
-
-struct Uniforms {
-    mvp: mat4x4<f32>,
-}
-
-@group(0) @binding(0) var<uniform> uniforms: Uniforms;
-
-@vertex
-fn vs_main(input: VertexInput) -> VertexOutput {
-    var output: VertexOutput;
-    output.position = uniforms.mvp * vec4<f32>(input.position, 1.0);
-    output.color = input.color;
-    return output;
-}
-
-@fragment
-fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(input.color, 1.0);
-}
-"#;
-
-        let tracker = ApiCoverageTracker::global();
-
-        tracker.record(ApiCategory::Shader, "create_shader_module");
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Pipeline Preview Shader"),
-            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
-        });
-
-        // Create bind group layout for uniforms
-        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Pipeline Preview Bind Group Layout"),
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-        });
-
-        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Pipeline Preview Layout"),
-            bind_group_layouts: &[Some(&bind_group_layout)],
-            immediate_size: 0,
-        });
-
-        // Convert our custom types to wgpu types
-        let topology = match primitive.topology {
-            PrimitiveTopology::PointList => wgpu::PrimitiveTopology::PointList,
-            PrimitiveTopology::LineList => wgpu::PrimitiveTopology::LineList,
-            PrimitiveTopology::LineStrip => wgpu::PrimitiveTopology::LineStrip,
-            PrimitiveTopology::TriangleList => wgpu::PrimitiveTopology::TriangleList,
-            PrimitiveTopology::TriangleStrip => wgpu::PrimitiveTopology::TriangleStrip,
-        };
-
-        let cull_mode = match primitive.cull_mode {
-            CullMode::None => None,
-            CullMode::Front => Some(wgpu::Face::Front),
-            CullMode::Back => Some(wgpu::Face::Back),
-        };
-
-        let front_face = match primitive.front_face {
-            FrontFace::Ccw => wgpu::FrontFace::Ccw,
-            FrontFace::Cw => wgpu::FrontFace::Cw,
-        };
-
-        // Build primitive state
-        let primitive_state = wgpu::PrimitiveState {
-            topology,
-            strip_index_format: None,
-            front_face,
-            cull_mode,
-            unclipped_depth: false,
-            polygon_mode: wgpu::PolygonMode::Fill,
-            conservative: false,
-        };
-
-        // Build depth stencil state
-        let depth_stencil_state = depth_stencil.map(|ds| {
-            let compare = match ds.depth_compare {
-                CompareFunction::Never => wgpu::CompareFunction::Never,
-                CompareFunction::Less => wgpu::CompareFunction::Less,
-                CompareFunction::Equal => wgpu::CompareFunction::Equal,
-                CompareFunction::LessEqual => wgpu::CompareFunction::LessEqual,
-                CompareFunction::Greater => wgpu::CompareFunction::Greater,
-                CompareFunction::NotEqual => wgpu::CompareFunction::NotEqual,
-                CompareFunction::GreaterEqual => wgpu::CompareFunction::GreaterEqual,
-                CompareFunction::Always => wgpu::CompareFunction::Always,
-            };
-
-            wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Depth24Plus,
-                depth_write_enabled: Some(ds.depth_write_enabled),
-                depth_compare: Some(compare),
-                stencil: wgpu::StencilState {
-                    front: wgpu::StencilFaceState::IGNORE,
-                    back: wgpu::StencilFaceState::IGNORE,
-                    read_mask: 0,
-                    write_mask: 0,
-                },
-                bias: wgpu::DepthBiasState::default(),
-            }
-        });
-
-        // Build blend state
-        let blend_state = blend.map(|b| {
-            let convert_blend_factor = |factor| match factor {
-                crate::render_pipeline::BlendFactor::Zero => wgpu::BlendFactor::Zero,
-                crate::render_pipeline::BlendFactor::One => wgpu::BlendFactor::One,
-                crate::render_pipeline::BlendFactor::Src => wgpu::BlendFactor::Src,
-                crate::render_pipeline::BlendFactor::OneMinusSrc => wgpu::BlendFactor::OneMinusSrc,
-                crate::render_pipeline::BlendFactor::SrcAlpha => wgpu::BlendFactor::SrcAlpha,
-                crate::render_pipeline::BlendFactor::OneMinusSrcAlpha => {
-                    wgpu::BlendFactor::OneMinusSrcAlpha
-                }
-                crate::render_pipeline::BlendFactor::Dst => wgpu::BlendFactor::Dst,
-                crate::render_pipeline::BlendFactor::OneMinusDst => wgpu::BlendFactor::OneMinusDst,
-                crate::render_pipeline::BlendFactor::DstAlpha => wgpu::BlendFactor::DstAlpha,
-                crate::render_pipeline::BlendFactor::OneMinusDstAlpha => {
-                    wgpu::BlendFactor::OneMinusDstAlpha
-                }
-                crate::render_pipeline::BlendFactor::SrcAlphaSaturated => {
-                    wgpu::BlendFactor::SrcAlphaSaturated
-                }
-                crate::render_pipeline::BlendFactor::Constant => wgpu::BlendFactor::Constant,
-                crate::render_pipeline::BlendFactor::OneMinusConstant => {
-                    wgpu::BlendFactor::OneMinusConstant
-                }
-            };
-
-            let convert_blend_operation = |op| match op {
-                crate::render_pipeline::BlendOperation::Add => wgpu::BlendOperation::Add,
-                crate::render_pipeline::BlendOperation::Subtract => wgpu::BlendOperation::Subtract,
-                crate::render_pipeline::BlendOperation::ReverseSubtract => {
-                    wgpu::BlendOperation::ReverseSubtract
-                }
-                crate::render_pipeline::BlendOperation::Min => wgpu::BlendOperation::Min,
-                crate::render_pipeline::BlendOperation::Max => wgpu::BlendOperation::Max,
-            };
-
-            wgpu::BlendState {
-                color: wgpu::BlendComponent {
-                    src_factor: convert_blend_factor(b.color.src_factor),
-                    dst_factor: convert_blend_factor(b.color.dst_factor),
-                    operation: convert_blend_operation(b.color.operation),
-                },
-                alpha: wgpu::BlendComponent {
-                    src_factor: convert_blend_factor(b.alpha.src_factor),
-                    dst_factor: convert_blend_factor(b.alpha.dst_factor),
-                    operation: convert_blend_operation(b.alpha.operation),
-                },
-            }
-        });
-
-        // Build multisample state
-        let multisample_state = wgpu::MultisampleState {
-            count: multisample.count,
-            mask: !0,
-            alpha_to_coverage_enabled: multisample.alpha_to_coverage_enabled,
+        crate::cpu_scope!("Create preview pipeline");
+        if self.pipeline_layout.is_none() {
+            self.init_layout(device);
+        }
+        let Some(layout) = &self.pipeline_layout else {
+            return;
         };
 
-        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Pipeline Preview Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<PreviewVertex>() as wgpu::BufferAddress,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &[
-                        wgpu::VertexAttribute {
-                            format: wgpu::VertexFormat::Float32x3,
-                            offset: 0,
-                            shader_location: 0,
-                        },
-                        wgpu::VertexAttribute {
-                            format: wgpu::VertexFormat::Float32x3,
-                            offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
-                            shader_location: 1,
-                        },
-                    ],
-                }],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                    blend: blend_state,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: primitive_state,
-            depth_stencil: depth_stencil_state,
-            multisample: multisample_state,
-            multiview_mask: None,
-            cache: None,
-        });
-
-        self.pipeline = Some(pipeline);
-        self.bind_group_layout = Some(bind_group_layout);
+        let descriptor = preview_descriptor(device, primitive, depth_stencil, blend, multisample);
+        match cache.get_or_create(
+            device,
+            layout,
+            &descriptor,
+            &self.shader,
+            Some(&self.shader),
+        ) {
+            Ok(pipeline) => self.pipeline = Some(pipeline),
+            Err(e) => log::error!("Failed to create preview pipeline: {}", e),
+        }
     }
 
-    /// Render the preview with the current pipeline configuration
+    /// Render the mesh nodes of `scene` with the current pipeline
+    /// configuration, seen through `camera`
     pub fn render(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         delta_time: f32,
+        camera: &Camera,
+        scene: &Scene,
     ) -> Option<&wgpu::TextureView> {
+        crate::cpu_scope!("Render pipeline preview");
         let tracker = ApiCoverageTracker::global();
 
         self.time += delta_time;
 
-        // Create MVP matrix for rotating cube
+        // Spin the whole scene the way the single cube always has
         let aspect = self.width as f32 / self.height as f32;
-        let projection = perspective_matrix(45.0_f32.to_radians(), aspect, 0.1, 100.0);
-        let view = view_matrix([0.0, 0.0, 3.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
-        let model = rotation_matrix_y(self.time) * rotation_matrix_x(self.time * 0.5);
-        let mvp = projection * view * model;
-
-        // Create uniform buffer with MVP matrix
-        tracker.record(ApiCategory::Buffer, "create_buffer");
-        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Pipeline Preview Uniform Buffer"),
-            contents: bytemuck::cast_slice(mvp.as_slice()),
-            usage: wgpu::BufferUsages::UNIFORM,
-        });
+        let spin = if scene.spin {
+            mat4_mul(&rotation_y(self.time), &rotation_x(self.time * 0.5))
+        } else {
+            MAT4_IDENTITY
+        };
+        let view_projection = mat4_mul(&camera.view_projection(aspect), &spin);
+
+        // One uniform slot per drawn node, selected with a dynamic offset
+        let draws = scene.draw_list();
+        self.update_primitives(device, &draws);
+        let stride = uniform_stride(device);
+        let mut contents = vec![0u8; stride as usize * draws.len().max(1)];
+        for (slot, draw) in contents.chunks_exact_mut(stride as usize).zip(&draws) {
+            let uniforms = PreviewUniforms {
+                mvp: mat4_mul(&view_projection, &draw.model),
+                tint: draw.tint,
+            };
+            slot[..UNIFORMS_SIZE as usize].copy_from_slice(bytemuck::bytes_of(&uniforms));
+        }
 
-        // Create bind group using stored bind group layout
-        if let Some(bind_group_layout) = &self.bind_group_layout {
-            tracker.record(ApiCategory::BindGroup, "create_bind_group");
-            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Pipeline Preview Bind Group"),
-                layout: bind_group_layout,
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: uniform_buffer.as_entire_binding(),
-                }],
-            });
+        self.reserve_uniforms(device, contents.len() as u64);
+        if let (Some(uniform_buffer), Some(bind_group)) =
+            (&self.uniform_buffer, &self.uniform_bind_group)
+        {
+            tracker.record(ApiCategory::Queue, "write_buffer");
+            upload_belt::write_buffer(queue, uniform_buffer, 0, &contents);
 
             // Render to the preview texture
             tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
@@ -591,26 +704,52 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
                         multiview_mask: None,
                     });
 
-                    // Render the cube
+                    // Render the scene
                     if let (Some(pipeline), Some(vertex_buffer), Some(index_buffer)) =
                         (&self.pipeline, &self.vertex_buffer, &self.index_buffer)
                     {
                         tracker.record(ApiCategory::RenderPass, "set_pipeline");
                         render_pass.set_pipeline(pipeline);
-                        tracker.record(ApiCategory::RenderPass, "set_bind_group");
-                        render_pass.set_bind_group(0, &bind_group, &[]);
-                        tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
-                        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-                        tracker.record(ApiCategory::RenderPass, "set_index_buffer");
-                        render_pass
-                            .set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-                        tracker.record(ApiCategory::RenderPass, "draw_indexed");
-                        render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+                        for (slot, draw) in draws.iter().enumerate() {
+                            let (vertices, indices, format, range) = match draw.mesh {
+                                MeshRef::Primitive(primitive) => {
+                                    let Some((_, mesh)) =
+                                        self.primitives.iter().find(|(p, _)| *p == primitive)
+                                    else {
+                                        continue;
+                                    };
+                                    (
+                                        &mesh.vertex_buffer,
+                                        &mesh.index_buffer,
+                                        wgpu::IndexFormat::Uint32,
+                                        0..mesh.index_count,
+                                    )
+                                }
+                                mesh => (
+                                    vertex_buffer,
+                                    index_buffer,
+                                    wgpu::IndexFormat::Uint16,
+                                    mesh_indices(mesh),
+                                ),
+                            };
+                            tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+                            render_pass.set_vertex_buffer(0, vertices.slice(..));
+                            tracker.record(ApiCategory::RenderPass, "set_index_buffer");
+                            render_pass.set_index_buffer(indices.slice(..), format);
+                            tracker.record(ApiCategory::RenderPass, "set_bind_group");
+                            render_pass.set_bind_group(
+                                0,
+                                bind_group,
+                                &[(slot as u64 * stride) as u32],
+                            );
+                            tracker.record(ApiCategory::RenderPass, "draw_indexed");
+                            render_pass.draw_indexed(range, 0, 0..1);
+                        }
                     }
                 }
 
                 tracker.record(ApiCategory::Queue, "submit");
-                queue.submit(Some(encoder.finish()));
+                upload_belt::submit(queue, Some(encoder.finish()));
             }
         }
 
@@ -639,91 +778,18 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
         self.texture_id
     }
 
+    /// The texture the preview renders into
+    pub fn texture(&self) -> Option<&wgpu::Texture> {
+        self.render_texture.as_ref()
+    }
+
     /// Get preview texture size
     pub fn size(&self) -> (u32, u32) {
         (self.width, self.height)
     }
-}
-
-// Matrix helper functions
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Matrix4 {
-    data: [[f32; 4]; 4],
-}
-
-impl Matrix4 {
-    fn as_slice(&self) -> &[f32] {
-        bytemuck::cast_slice(&self.data)
-    }
-}
-
-impl std::ops::Mul for Matrix4 {
-    type Output = Matrix4;
-
-    #[allow(clippy::needless_range_loop)]
-    fn mul(self, rhs: Matrix4) -> Matrix4 {
-        let mut result = [[0.0; 4]; 4];
-        for i in 0..4 {
-            for j in 0..4 {
-                for k in 0..4 {
-                    result[i][j] += self.data[i][k] * rhs.data[k][j];
-                }
-            }
-        }
-        Matrix4 { data: result }
-    }
-}
-
-fn perspective_matrix(fovy: f32, aspect: f32, near: f32, far: f32) -> Matrix4 {
-    let f = 1.0 / (fovy / 2.0).tan();
-    Matrix4 {
-        data: [
-            [f / aspect, 0.0, 0.0, 0.0],
-            [0.0, f, 0.0, 0.0],
-            [0.0, 0.0, (far + near) / (near - far), -1.0],
-            [0.0, 0.0, (2.0 * far * near) / (near - far), 0.0],
-        ],
-    }
-}
-
-fn view_matrix(eye: [f32; 3], center: [f32; 3], up: [f32; 3]) -> Matrix4 {
-    let f = normalize([center[0] - eye[0], center[1] - eye[1], center[2] - eye[2]]);
-    let s = normalize(cross(f, up));
-    let u = cross(s, f);
-
-    Matrix4 {
-        data: [
-            [s[0], u[0], -f[0], 0.0],
-            [s[1], u[1], -f[1], 0.0],
-            [s[2], u[2], -f[2], 0.0],
-            [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
-        ],
-    }
-}
-
-fn rotation_matrix_y(angle: f32) -> Matrix4 {
-    let c = angle.cos();
-    let s = angle.sin();
-    Matrix4 {
-        data: [
-            [c, 0.0, s, 0.0],
-            [0.0, 1.0, 0.0, 0.0],
-            [-s, 0.0, c, 0.0],
-            [0.0, 0.0, 0.0, 1.0],
-        ],
-    }
-}
 
-fn rotation_matrix_x(angle: f32) -> Matrix4 {
-    let c = angle.cos();
-    let s = angle.sin();
-    Matrix4 {
-        data: [
-            [1.0, 0.0, 0.0, 0.0],
-            [0.0, c, -s, 0.0],
-            [0.0, s, c, 0.0],
-            [0.0, 0.0, 0.0, 1.0],
-        ],
+    /// Set the animation time, e.g. to keep two previews in lockstep
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
     }
 }
@@ -0,0 +1,645 @@
+//! Pixel picker that reads back the texels under the cursor of a preview
+//!
+//! Holding Shift over a preview copies a small region around the hovered
+//! texel into a staging buffer. The copy is mapped asynchronously and
+//! collected on a later frame, so the UI never waits on the GPU. Texels are
+//! decoded per format into their stored values (bytes, half and full float
+//! bits, integers) next to the value a shader would read.
+
+use std::fmt;
+use std::sync::mpsc;
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::visual_regression::formats::f16_to_f32;
+
+/// Texels read on each side of the picked one, giving a 5×5 region
+pub const PICK_RADIUS: u32 = 2;
+
+/// Errors from picking texels out of a texture
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickError {
+    UnsupportedFormat(wgpu::TextureFormat),
+    Multisampled,
+    NotCopyable,
+    OutOfBounds { x: u32, y: u32 },
+    Readback(String),
+}
+
+impl fmt::Display for PickError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickError::UnsupportedFormat(format) => {
+                write!(f, "Texels of {:?} can't be decoded", format)
+            }
+            PickError::Multisampled => write!(f, "Multisampled textures can't be copied"),
+            PickError::NotCopyable => write!(f, "The texture was created without COPY_SRC"),
+            PickError::OutOfBounds { x, y } => {
+                write!(f, "Texel ({}, {}) is outside the texture", x, y)
+            }
+            PickError::Readback(msg) => write!(f, "Pixel readback failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PickError {}
+
+/// How each channel of a format is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChannelKind {
+    Unorm8,
+    Uint8,
+    Float16,
+    Float32,
+    Uint32,
+    Sint32,
+}
+
+impl ChannelKind {
+    fn size(self) -> usize {
+        match self {
+            ChannelKind::Unorm8 | ChannelKind::Uint8 => 1,
+            ChannelKind::Float16 => 2,
+            ChannelKind::Float32 | ChannelKind::Uint32 | ChannelKind::Sint32 => 4,
+        }
+    }
+}
+
+/// Channel storage of a format and its channel names in memory order
+fn channel_layout(format: wgpu::TextureFormat) -> Option<(ChannelKind, &'static str)> {
+    use wgpu::TextureFormat as F;
+    Some(match format {
+        F::R8Unorm => (ChannelKind::Unorm8, "R"),
+        F::Rg8Unorm => (ChannelKind::Unorm8, "RG"),
+        F::Rgba8Unorm | F::Rgba8UnormSrgb => (ChannelKind::Unorm8, "RGBA"),
+        F::Bgra8Unorm | F::Bgra8UnormSrgb => (ChannelKind::Unorm8, "BGRA"),
+        F::R8Uint => (ChannelKind::Uint8, "R"),
+        F::Rgba8Uint => (ChannelKind::Uint8, "RGBA"),
+        F::R16Float => (ChannelKind::Float16, "R"),
+        F::Rg16Float => (ChannelKind::Float16, "RG"),
+        F::Rgba16Float => (ChannelKind::Float16, "RGBA"),
+        F::R32Float => (ChannelKind::Float32, "R"),
+        F::Rg32Float => (ChannelKind::Float32, "RG"),
+        F::Rgba32Float => (ChannelKind::Float32, "RGBA"),
+        F::R32Uint => (ChannelKind::Uint32, "R"),
+        F::Rgba32Uint => (ChannelKind::Uint32, "RGBA"),
+        F::R32Sint => (ChannelKind::Sint32, "R"),
+        F::Rgba32Sint => (ChannelKind::Sint32, "RGBA"),
+        _ => return None,
+    })
+}
+
+/// Whether texels of `format` can be picked
+pub fn is_pickable(format: wgpu::TextureFormat) -> bool {
+    channel_layout(format).is_some()
+}
+
+/// Stored value of one channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChannelValue {
+    Unorm8(u8),
+    Uint8(u8),
+    /// Bits of a half float
+    Float16(u16),
+    Float32(f32),
+    Uint32(u32),
+    Sint32(i32),
+}
+
+impl ChannelValue {
+    /// The value a shader reads: normalized for unorm channels
+    pub fn to_f32(self) -> f32 {
+        match self {
+            ChannelValue::Unorm8(v) => v as f32 / 255.0,
+            ChannelValue::Uint8(v) => v as f32,
+            ChannelValue::Float16(bits) => f16_to_f32(bits),
+            ChannelValue::Float32(v) => v,
+            ChannelValue::Uint32(v) => v as f32,
+            ChannelValue::Sint32(v) => v as f32,
+        }
+    }
+
+    /// The stored bits: the integer, or the float's bits in hex
+    pub fn raw_text(self) -> String {
+        match self {
+            ChannelValue::Unorm8(v) | ChannelValue::Uint8(v) => format!("{} (0x{:02X})", v, v),
+            ChannelValue::Float16(bits) => format!("0x{:04X}", bits),
+            ChannelValue::Float32(v) => format!("0x{:08X}", v.to_bits()),
+            ChannelValue::Uint32(v) => v.to_string(),
+            ChannelValue::Sint32(v) => v.to_string(),
+        }
+    }
+
+    /// The shader-visible value, exact for integers
+    pub fn value_text(self) -> String {
+        match self {
+            ChannelValue::Unorm8(_) | ChannelValue::Float16(_) => format!("{:.6}", self.to_f32()),
+            ChannelValue::Float32(v) => format!("{}", v),
+            ChannelValue::Uint8(v) => v.to_string(),
+            ChannelValue::Uint32(v) => v.to_string(),
+            ChannelValue::Sint32(v) => v.to_string(),
+        }
+    }
+}
+
+/// Decode one texel into named channels in RGBA order
+pub fn decode_texel(
+    format: wgpu::TextureFormat,
+    bytes: &[u8],
+) -> Result<Vec<(char, ChannelValue)>, PickError> {
+    let (kind, names) = channel_layout(format).ok_or(PickError::UnsupportedFormat(format))?;
+    let size = kind.size();
+    if bytes.len() < size * names.len() {
+        return Err(PickError::Readback(format!(
+            "expected {} bytes per texel, got {}",
+            size * names.len(),
+            bytes.len()
+        )));
+    }
+    let mut channels: Vec<(char, ChannelValue)> = names
+        .chars()
+        .zip(bytes.chunks_exact(size))
+        .map(|(name, b)| {
+            let value = match kind {
+                ChannelKind::Unorm8 => ChannelValue::Unorm8(b[0]),
+                ChannelKind::Uint8 => ChannelValue::Uint8(b[0]),
+                ChannelKind::Float16 => ChannelValue::Float16(u16::from_le_bytes([b[0], b[1]])),
+                ChannelKind::Float32 => {
+                    ChannelValue::Float32(f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                }
+                ChannelKind::Uint32 => {
+                    ChannelValue::Uint32(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                }
+                ChannelKind::Sint32 => {
+                    ChannelValue::Sint32(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                }
+            };
+            (name, value)
+        })
+        .collect();
+    channels.sort_by_key(|(name, _)| "RGBA".find(*name));
+    Ok(channels)
+}
+
+/// Origin and size of the region around `center`, clamped to the texture
+pub fn pick_region(center: (u32, u32), size: (u32, u32), radius: u32) -> ((u32, u32), (u32, u32)) {
+    let origin = (
+        center.0.saturating_sub(radius),
+        center.1.saturating_sub(radius),
+    );
+    let end = (
+        (center.0 + radius + 1).min(size.0),
+        (center.1 + radius + 1).min(size.1),
+    );
+    (origin, (end.0 - origin.0, end.1 - origin.1))
+}
+
+/// Texels read back around a picked texel
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelRegion {
+    pub format: wgpu::TextureFormat,
+    /// Texel under the cursor
+    pub center: (u32, u32),
+    /// Top-left texel of the region
+    pub origin: (u32, u32),
+    pub width: u32,
+    pub height: u32,
+    /// Tightly packed texels, row by row
+    data: Vec<u8>,
+}
+
+impl PixelRegion {
+    pub fn new(
+        format: wgpu::TextureFormat,
+        center: (u32, u32),
+        origin: (u32, u32),
+        width: u32,
+        height: u32,
+        data: Vec<u8>,
+    ) -> Self {
+        Self {
+            format,
+            center,
+            origin,
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Channels of the texel at `(x, y)` in texture coordinates
+    pub fn texel(&self, x: u32, y: u32) -> Option<Vec<(char, ChannelValue)>> {
+        let (column, row) = (x.checked_sub(self.origin.0)?, y.checked_sub(self.origin.1)?);
+        if column >= self.width || row >= self.height {
+            return None;
+        }
+        let texel_size = self.format.block_copy_size(None)? as usize;
+        let start = (row * self.width + column) as usize * texel_size;
+        decode_texel(self.format, self.data.get(start..start + texel_size)?).ok()
+    }
+
+    /// Channels of the picked texel
+    pub fn center_texel(&self) -> Option<Vec<(char, ChannelValue)>> {
+        self.texel(self.center.0, self.center.1)
+    }
+
+    /// Shader-visible RGBA of a texel; missing channels read as 0, alpha as 1
+    pub fn rgba(&self, x: u32, y: u32) -> Option<[f32; 4]> {
+        let mut rgba = [0.0, 0.0, 0.0, 1.0];
+        for (name, value) in self.texel(x, y)? {
+            rgba["RGBA".find(name)?] = value.to_f32();
+        }
+        Some(rgba)
+    }
+}
+
+/// A copy that has been submitted but not mapped yet
+struct PendingPick {
+    buffer: wgpu::Buffer,
+    receiver: mpsc::Receiver<Result<(), wgpu::BufferAsyncError>>,
+    format: wgpu::TextureFormat,
+    center: (u32, u32),
+    origin: (u32, u32),
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+}
+
+/// Reads back the region around a texel without blocking the frame
+///
+/// At most one copy is in flight; [`PixelPicker::pick`] collects the
+/// previous one before starting the next, so the values follow the cursor
+/// and animated previews a frame or two behind.
+#[derive(Default)]
+pub struct PixelPicker {
+    pending: Option<PendingPick>,
+    region: Option<PixelRegion>,
+    error: Option<PickError>,
+}
+
+impl PixelPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Most recently collected region
+    pub fn region(&self) -> Option<&PixelRegion> {
+        self.region.as_ref()
+    }
+
+    /// Error from the last pick, if it failed
+    pub fn error(&self) -> Option<&PickError> {
+        self.error.as_ref()
+    }
+
+    /// Whether a copy is waiting to be mapped
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Forget the region and drop any copy in flight
+    pub fn clear(&mut self) {
+        self.pending = None;
+        self.region = None;
+        self.error = None;
+    }
+
+    /// Collect a finished copy and start one for the region around `center`
+    pub fn pick(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+        center: (u32, u32),
+    ) -> Result<(), PickError> {
+        let result = self.collect(device).and_then(|_| {
+            if self.pending.is_none() {
+                self.pending = Some(start_pick(device, queue, texture, center)?);
+            }
+            Ok(())
+        });
+        self.error = result.as_ref().err().cloned();
+        result
+    }
+
+    /// Poll the device and take the copy in flight if it has been mapped
+    ///
+    /// Returns whether a new region arrived.
+    pub fn collect(&mut self, device: &wgpu::Device) -> Result<bool, PickError> {
+        let Some(pending) = &self.pending else {
+            return Ok(false);
+        };
+        let _ = device.poll(wgpu::PollType::Poll);
+        match pending.receiver.try_recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                self.pending = None;
+                return Err(PickError::Readback(e.to_string()));
+            }
+            Err(mpsc::TryRecvError::Empty) => return Ok(false),
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.pending = None;
+                return Err(PickError::Readback("the copy was dropped".to_string()));
+            }
+        }
+
+        let pending = self.pending.take().expect("checked above");
+        let texel_size = pending.format.block_copy_size(None).unwrap_or(0);
+        let row_bytes = (pending.width * texel_size) as usize;
+        let slice = pending.buffer.slice(..);
+        let mapped = slice.get_mapped_range();
+        let mut data = Vec::with_capacity(row_bytes * pending.height as usize);
+        for row in mapped
+            .chunks(pending.padded_bytes_per_row as usize)
+            .take(pending.height as usize)
+        {
+            data.extend_from_slice(&row[..row_bytes]);
+        }
+        drop(mapped);
+        pending.buffer.unmap();
+
+        self.region = Some(PixelRegion::new(
+            pending.format,
+            pending.center,
+            pending.origin,
+            pending.width,
+            pending.height,
+            data,
+        ));
+        Ok(true)
+    }
+
+    /// Show the picked texels in a tooltip while Shift is held over an image
+    ///
+    /// `response` is the image showing `texture`, stretched over its rect.
+    pub fn image_hover_ui(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+    ) {
+        let size = (texture.width(), texture.height());
+        let hovered = response
+            .hover_pos()
+            .filter(|_| ui.input(|i| i.modifiers.shift))
+            .map(|pos| {
+                let at = pos - response.rect.min;
+                let (x, y) = (
+                    at.x / response.rect.width() * size.0 as f32,
+                    at.y / response.rect.height() * size.1 as f32,
+                );
+                ((x as u32).min(size.0 - 1), (y as u32).min(size.1 - 1))
+            });
+        let Some(center) = hovered else {
+            if response.hovered() {
+                response
+                    .clone()
+                    .on_hover_text("Hold Shift to inspect pixels");
+            }
+            if self.region.is_some() || self.pending.is_some() || self.error.is_some() {
+                self.clear();
+            }
+            return;
+        };
+
+        let _ = self.pick(device, queue, texture, center);
+        // Keep polling until the copy comes back
+        ui.ctx().request_repaint();
+        response
+            .clone()
+            .on_hover_ui_at_pointer(|ui| self.tooltip_ui(ui, center));
+    }
+
+    fn tooltip_ui(&self, ui: &mut egui::Ui, center: (u32, u32)) {
+        ui.strong(format!("Pixel ({}, {})", center.0, center.1));
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), error.to_string());
+            return;
+        }
+        let Some(region) = &self.region else {
+            ui.weak("Reading back…");
+            return;
+        };
+        ui.label(format!("{:?}", region.format));
+
+        // The region as enlarged swatches, the picked texel outlined
+        let cell = 12.0;
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(region.width as f32 * cell, region.height as f32 * cell),
+            egui::Sense::hover(),
+        );
+        let top_left = response.rect.min;
+        for row in 0..region.height {
+            for column in 0..region.width {
+                let (x, y) = (region.origin.0 + column, region.origin.1 + row);
+                let Some(rgba) = region.rgba(x, y) else {
+                    continue;
+                };
+                let [r, g, b, a] = rgba.map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8);
+                let rect = egui::Rect::from_min_size(
+                    top_left + egui::vec2(column as f32 * cell, row as f32 * cell),
+                    egui::vec2(cell, cell),
+                );
+                painter.rect_filled(rect, 0.0, egui::Color32::from_rgba_unmultiplied(r, g, b, a));
+                if (x, y) == region.center {
+                    painter.rect_stroke(
+                        rect,
+                        0.0,
+                        egui::Stroke::new(2.0, egui::Color32::WHITE),
+                        egui::StrokeKind::Inside,
+                    );
+                }
+            }
+        }
+
+        let Some(channels) = region.center_texel() else {
+            return;
+        };
+        egui::Grid::new("pixel_picker_channels")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Channel");
+                ui.strong("Raw");
+                ui.strong("Value");
+                ui.end_row();
+                for (name, value) in channels {
+                    ui.monospace(name.to_string());
+                    ui.monospace(value.raw_text());
+                    ui.monospace(value.value_text());
+                    ui.end_row();
+                }
+            });
+        if region.format.is_srgb() {
+            ui.weak("Values are sRGB-encoded; shaders sampling it read them as linear");
+        }
+    }
+}
+
+/// Copy the region around `center` into a staging buffer and map it
+fn start_pick(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    center: (u32, u32),
+) -> Result<PendingPick, PickError> {
+    let format = texture.format();
+    let texel_size = format
+        .block_copy_size(None)
+        .filter(|_| is_pickable(format))
+        .ok_or(PickError::UnsupportedFormat(format))?;
+    if texture.sample_count() != 1 {
+        return Err(PickError::Multisampled);
+    }
+    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+        return Err(PickError::NotCopyable);
+    }
+    let size = (texture.width(), texture.height());
+    if center.0 >= size.0 || center.1 >= size.1 {
+        return Err(PickError::OutOfBounds {
+            x: center.0,
+            y: center.1,
+        });
+    }
+
+    let (origin, (width, height)) = pick_region(center, size, PICK_RADIUS);
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_bytes_per_row = (width * texel_size).div_ceil(align) * align;
+    let tracker = ApiCoverageTracker::global();
+
+    tracker.record(ApiCategory::Buffer, "create_buffer");
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Pixel Picker Staging Buffer"),
+        size: (padded_bytes_per_row * height) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Pixel Picker Encoder"),
+    });
+    tracker.record(ApiCategory::CommandEncoder, "copy_texture_to_buffer");
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d {
+                x: origin.0,
+                y: origin.1,
+                z: 0,
+            },
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    tracker.record(ApiCategory::Queue, "submit");
+    queue.submit(Some(encoder.finish()));
+
+    let (sender, receiver) = mpsc::channel();
+    tracker.record(ApiCategory::Buffer, "map_async");
+    buffer
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+    Ok(PendingPick {
+        buffer,
+        receiver,
+        format,
+        center,
+        origin,
+        width,
+        height,
+        padded_bytes_per_row,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_region_clamps_to_texture() {
+        assert_eq!(pick_region((10, 10), (64, 64), 2), ((8, 8), (5, 5)));
+        assert_eq!(pick_region((0, 1), (64, 64), 2), ((0, 0), (3, 4)));
+        assert_eq!(pick_region((63, 62), (64, 64), 2), ((61, 60), (3, 4)));
+        assert_eq!(pick_region((0, 0), (1, 1), 2), ((0, 0), (1, 1)));
+    }
+
+    #[test]
+    fn test_decode_texel_reorders_bgra() {
+        let channels = decode_texel(wgpu::TextureFormat::Bgra8Unorm, &[10, 20, 30, 255]).unwrap();
+        assert_eq!(
+            channels,
+            vec![
+                ('R', ChannelValue::Unorm8(30)),
+                ('G', ChannelValue::Unorm8(20)),
+                ('B', ChannelValue::Unorm8(10)),
+                ('A', ChannelValue::Unorm8(255)),
+            ]
+        );
+        assert_eq!(channels[3].1.to_f32(), 1.0);
+        assert_eq!(channels[0].1.raw_text(), "30 (0x1E)");
+    }
+
+    #[test]
+    fn test_decode_texel_float_formats() {
+        // 1.0 and -2.0 as halves
+        let bytes = [0x00, 0x3C, 0x00, 0xC0];
+        let channels = decode_texel(wgpu::TextureFormat::Rg16Float, &bytes).unwrap();
+        assert_eq!(channels[0].1.to_f32(), 1.0);
+        assert_eq!(channels[1].1.to_f32(), -2.0);
+        assert_eq!(channels[0].1.raw_text(), "0x3C00");
+
+        let channels = decode_texel(wgpu::TextureFormat::R32Float, &0.1f32.to_le_bytes()).unwrap();
+        assert_eq!(channels[0].1.raw_text(), "0x3DCCCCCD");
+        assert_eq!(channels[0].1.value_text(), "0.1");
+
+        let channels = decode_texel(wgpu::TextureFormat::R32Sint, &(-7i32).to_le_bytes()).unwrap();
+        assert_eq!(channels[0].1.value_text(), "-7");
+    }
+
+    #[test]
+    fn test_decode_texel_rejects_unsupported_and_short() {
+        assert_eq!(
+            decode_texel(wgpu::TextureFormat::Depth32Float, &[0; 4]),
+            Err(PickError::UnsupportedFormat(
+                wgpu::TextureFormat::Depth32Float
+            ))
+        );
+        assert!(decode_texel(wgpu::TextureFormat::Rgba8Unorm, &[0; 3]).is_err());
+    }
+
+    #[test]
+    fn test_region_texel_lookup() {
+        // A 2×2 region at (4, 6) of R8Unorm texels 0, 1, 2, 3
+        let region = PixelRegion::new(
+            wgpu::TextureFormat::R8Unorm,
+            (5, 7),
+            (4, 6),
+            2,
+            2,
+            vec![0, 1, 2, 3],
+        );
+        assert_eq!(
+            region.center_texel(),
+            Some(vec![('R', ChannelValue::Unorm8(3))])
+        );
+        assert_eq!(region.rgba(5, 6), Some([1.0 / 255.0, 0.0, 0.0, 1.0]));
+        assert_eq!(region.texel(3, 6), None);
+        assert_eq!(region.texel(6, 6), None);
+    }
+}
@@ -69,6 +69,11 @@ pub fn get_all_presets() -> Vec<ConfigPreset> {
     ]
 }
 
+/// Get the preset with the given id
+pub fn get_preset(id: &str) -> Option<ConfigPreset> {
+    get_all_presets().into_iter().find(|preset| preset.id == id)
+}
+
 /// Create PBR (Physically Based Rendering) material preset
 fn create_pbr_material_preset() -> ConfigPreset {
     let mut state = PlaygroundState::new();
@@ -212,6 +217,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         .to_string(),
         label: "pbr_material".to_string(),
         file_path: String::new(),
+        ..Default::default()
     });
 
     // Uniform buffer for material properties
@@ -239,6 +245,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         topology: "TriangleList".to_string(),
         cull_mode: "Back".to_string(),
         front_face: "Ccw".to_string(),
+        polygon_mode: "Fill".to_string(),
         enable_depth_stencil: true,
         depth_format: "Depth24Plus".to_string(),
         depth_write_enabled: true,
@@ -409,6 +416,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         .to_string(),
         label: "shadow_mapping".to_string(),
         file_path: String::new(),
+        ..Default::default()
     });
 
     // Shadow map texture (depth texture)
@@ -426,6 +434,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         usage_texture_binding: true,
         usage_storage_binding: false,
         usage_render_attachment: true,
+        image_data: None,
     });
 
     // Comparison sampler for shadow map
@@ -468,6 +477,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         topology: "TriangleList".to_string(),
         cull_mode: "Back".to_string(),
         front_face: "Ccw".to_string(),
+        polygon_mode: "Fill".to_string(),
         enable_depth_stencil: true,
         depth_format: "Depth24Plus".to_string(),
         depth_write_enabled: true,
@@ -648,6 +658,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 "#.to_string(),
         label: "post_processing".to_string(),
         file_path: String::new(),
+        ..Default::default()
     });
 
     // Input texture from previous render pass
@@ -665,6 +676,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         usage_texture_binding: true,
         usage_storage_binding: false,
         usage_render_attachment: true,
+        image_data: None,
     });
 
     // Linear sampler for post-processing
@@ -707,6 +719,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         topology: "TriangleList".to_string(),
         cull_mode: "None".to_string(),
         front_face: "Ccw".to_string(),
+        polygon_mode: "Fill".to_string(),
         enable_depth_stencil: false,
         depth_format: "Depth24Plus".to_string(),
         depth_write_enabled: false,
@@ -2,6 +2,8 @@
 ///
 /// This panel allows users to browse and load preset configurations
 /// for common rendering scenarios.
+use crate::command_palette::{Command, CommandKind, CommandRegistry};
+use crate::i18n::tr_args;
 use crate::preset::{get_all_presets, ConfigPreset, PresetCategory};
 use crate::state::PlaygroundState;
 use egui::{Color32, RichText};
@@ -29,6 +31,19 @@ impl PresetPanel {
         }
     }
 
+    /// Add a command applying each preset to the command palette
+    pub fn register_commands(&self, registry: &mut CommandRegistry) {
+        for preset in get_all_presets() {
+            registry.register(
+                Command::new(
+                    CommandKind::ApplyPreset(preset.id),
+                    tr_args("command-palette-apply-preset", &[("preset", &preset.name)]),
+                )
+                .with_keywords(preset.tags.iter().copied()),
+            );
+        }
+    }
+
     /// Render the preset panel UI
     pub fn ui(&mut self, ui: &mut egui::Ui) -> Option<PlaygroundState> {
         let mut state_to_load = None;
@@ -0,0 +1,723 @@
+//! Procedural textures for the Textures tab
+//!
+//! A [`Generator`] fills an image from a handful of [`ProceduralSettings`]:
+//! checkerboards, Perlin and simplex noise (summed over octaves), Worley
+//! cells, gradients and bricks. Every generator also gives a height for each
+//! pixel, which [`height_to_normal_map`] turns into a tangent-space normal
+//! map. Pixels are computed on the CPU from coordinates normalized to the
+//! image, so a small preview looks like the full-size bake.
+//!
+//! [`BakedTexture`] uploads a generated image into a GPU texture that can be
+//! bound like any other.
+
+use std::f32::consts::{PI, TAU};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::asset_loader::DecodedImage;
+use crate::job_system::{Job, JobSystem};
+use crate::texture::TextureBuilder;
+
+/// Largest width or height a texture is baked at
+pub const MAX_BAKE_SIZE: u32 = 8192;
+
+/// Pattern a procedural texture is made of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Generator {
+    /// Squares alternating between both colors
+    #[default]
+    Checkerboard,
+    /// Classic gradient noise on a square grid
+    Perlin,
+    /// Gradient noise on a triangular grid, with fewer axis-aligned artifacts
+    Simplex,
+    /// Distance to the nearest of randomly placed points, one per cell
+    Worley,
+    /// Blend between both colors across the image
+    Gradient,
+    /// Rows of bricks offset from each other, separated by mortar
+    Bricks,
+}
+
+impl Generator {
+    pub const ALL: [Generator; 6] = [
+        Generator::Checkerboard,
+        Generator::Perlin,
+        Generator::Simplex,
+        Generator::Worley,
+        Generator::Gradient,
+        Generator::Bricks,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Generator::Checkerboard => "Checkerboard",
+            Generator::Perlin => "Perlin Noise",
+            Generator::Simplex => "Simplex Noise",
+            Generator::Worley => "Worley Noise",
+            Generator::Gradient => "Gradient",
+            Generator::Bricks => "Bricks",
+        }
+    }
+
+    /// Whether the generator uses the seed and octave settings
+    pub fn is_noise(&self) -> bool {
+        matches!(self, Generator::Perlin | Generator::Simplex)
+    }
+}
+
+/// Shape of a [`Generator::Gradient`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum GradientKind {
+    /// Along a direction
+    #[default]
+    Linear,
+    /// Outward from the center
+    Radial,
+    /// Around the center
+    Angular,
+}
+
+impl GradientKind {
+    pub const ALL: [GradientKind; 3] = [
+        GradientKind::Linear,
+        GradientKind::Radial,
+        GradientKind::Angular,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GradientKind::Linear => "Linear",
+            GradientKind::Radial => "Radial",
+            GradientKind::Angular => "Angular",
+        }
+    }
+}
+
+/// Parameters of every generator; each one reads the fields it needs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProceduralSettings {
+    pub generator: Generator,
+    /// The first color, where the pattern is low (mortar for bricks)
+    pub low_color: [u8; 3],
+    /// The second color, where the pattern is high (bricks)
+    pub high_color: [u8; 3],
+    /// Checker squares, noise periods or Worley cells across the image
+    pub scale: f32,
+    /// Seed of the noise, Worley points and brick shades
+    pub seed: u32,
+    /// Noise layers of rising frequency added together
+    pub octaves: u32,
+    /// Amplitude of each octave relative to the one before
+    pub persistence: f32,
+    /// Frequency of each octave relative to the one before
+    pub lacunarity: f32,
+    /// Worley cells bright in the middle instead of dark
+    pub invert: bool,
+    pub gradient: GradientKind,
+    /// Direction of linear gradients and start of angular ones, in degrees
+    pub angle: f32,
+    pub brick_rows: u32,
+    pub brick_columns: u32,
+    /// Mortar thickness as a fraction of a brick's height
+    pub mortar: f32,
+    /// Shift of every other row, as a fraction of a brick's width
+    pub brick_offset: f32,
+    /// How much the shade of each brick varies
+    pub brick_variation: f32,
+    /// Output a normal map of the pattern's heights instead of its colors
+    pub normal_map: bool,
+    /// Steepness of the normal map's slopes
+    pub normal_strength: f32,
+}
+
+impl Default for ProceduralSettings {
+    fn default() -> Self {
+        Self {
+            generator: Generator::Checkerboard,
+            low_color: [100, 100, 100],
+            high_color: [200, 200, 200],
+            scale: 8.0,
+            seed: 0,
+            octaves: 4,
+            persistence: 0.5,
+            lacunarity: 2.0,
+            invert: false,
+            gradient: GradientKind::Linear,
+            angle: 0.0,
+            brick_rows: 8,
+            brick_columns: 4,
+            mortar: 0.1,
+            brick_offset: 0.5,
+            brick_variation: 0.2,
+            normal_map: false,
+            normal_strength: 8.0,
+        }
+    }
+}
+
+/// Hash of a lattice point, the source of every random value here
+fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    let mut h = (x as u32).wrapping_mul(0x8DA6_B343)
+        ^ (y as u32).wrapping_mul(0xD816_3841)
+        ^ seed.wrapping_mul(0xCB1A_B31F);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0x5BD1_E995);
+    h ^ (h >> 15)
+}
+
+/// Random value in [0, 1) for a lattice point
+fn random(x: i32, y: i32, seed: u32) -> f32 {
+    (hash(x, y, seed) >> 8) as f32 / (1 << 24) as f32
+}
+
+/// Unit gradient of a lattice point
+fn gradient(x: i32, y: i32, seed: u32) -> (f32, f32) {
+    let angle = random(x, y, seed) * TAU;
+    (angle.cos(), angle.sin())
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// 2D Perlin noise, roughly in [-1, 1]
+pub fn perlin(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let corner = |dx: i32, dy: i32| {
+        let (gx, gy) = gradient(ix + dx, iy + dy, seed);
+        gx * (fx - dx as f32) + gy * (fy - dy as f32)
+    };
+    // Quintic fade, so the noise is smooth across cells
+    let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let (u, v) = (fade(fx), fade(fy));
+    let value = lerp(
+        lerp(corner(0, 0), corner(1, 0), u),
+        lerp(corner(0, 1), corner(1, 1), u),
+        v,
+    );
+    value * std::f32::consts::SQRT_2
+}
+
+/// 2D simplex noise, roughly in [-1, 1]
+pub fn simplex(x: f32, y: f32, seed: u32) -> f32 {
+    // Skew to the square grid and back to the triangles
+    const F2: f32 = 0.366_025_42; // (sqrt(3) - 1) / 2
+    const G2: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+    let s = (x + y) * F2;
+    let (i, j) = ((x + s).floor(), (y + s).floor());
+    let t = (i + j) * G2;
+    let (x0, y0) = (x - (i - t), y - (j - t));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let corners = [
+        (0, 0, x0, y0),
+        (i1, j1, x0 - i1 as f32 + G2, y0 - j1 as f32 + G2),
+        (1, 1, x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2),
+    ];
+    let (i, j) = (i as i32, j as i32);
+    let sum: f32 = corners
+        .iter()
+        .map(|&(di, dj, cx, cy)| {
+            let falloff = 0.5 - cx * cx - cy * cy;
+            if falloff <= 0.0 {
+                return 0.0;
+            }
+            let (gx, gy) = gradient(i + di, j + dj, seed);
+            falloff.powi(4) * (gx * cx + gy * cy)
+        })
+        .sum();
+    70.0 * sum
+}
+
+/// Octaves of `noise` added together, mapped to [0, 1]
+fn fractal(settings: &ProceduralSettings, noise: fn(f32, f32, u32) -> f32, u: f32, v: f32) -> f32 {
+    let mut frequency = settings.scale;
+    let mut amplitude = 1.0;
+    let mut total = 0.0;
+    let mut total_amplitude = 0.0;
+    for octave in 0..settings.octaves.max(1) {
+        let seed = settings.seed.wrapping_add(octave);
+        total += amplitude * noise(u * frequency, v * frequency, seed);
+        total_amplitude += amplitude;
+        frequency *= settings.lacunarity;
+        amplitude *= settings.persistence;
+    }
+    (0.5 + 0.5 * total / total_amplitude).clamp(0.0, 1.0)
+}
+
+/// Distance from (`u`, `v`) to the nearest feature point, in cells, for a
+/// grid of `cells` by `cells` that wraps around so the texture tiles
+pub fn worley(u: f32, v: f32, cells: u32, seed: u32) -> f32 {
+    let cells = cells.max(1) as i32;
+    let (x, y) = (u * cells as f32, v * cells as f32);
+    let (cx, cy) = (x.floor() as i32, y.floor() as i32);
+    let mut nearest = f32::MAX;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let (nx, ny) = (cx + dx, cy + dy);
+            let (wx, wy) = (nx.rem_euclid(cells), ny.rem_euclid(cells));
+            let px = nx as f32 + random(wx, wy, seed);
+            let py = ny as f32 + random(wx, wy, seed ^ 0x9E37_79B9);
+            nearest = nearest.min((px - x).hypot(py - y));
+        }
+    }
+    nearest
+}
+
+fn gradient_value(settings: &ProceduralSettings, u: f32, v: f32) -> f32 {
+    let (x, y) = (u - 0.5, v - 0.5);
+    let angle = settings.angle.to_radians();
+    let value = match settings.gradient {
+        GradientKind::Linear => {
+            let (sin, cos) = angle.sin_cos();
+            // Half the image's extent along the direction, so the ends are
+            // reached at the corners
+            let extent = 0.5 * (cos.abs() + sin.abs());
+            0.5 + (x * cos + y * sin) / (2.0 * extent)
+        }
+        GradientKind::Radial => x.hypot(y) / 0.5f32.hypot(0.5),
+        GradientKind::Angular => (y.atan2(x) - angle + PI).rem_euclid(TAU) / TAU,
+    };
+    value.clamp(0.0, 1.0)
+}
+
+/// Height and, for bricks, shade of a brick at (`u`, `v`)
+fn bricks(settings: &ProceduralSettings, u: f32, v: f32) -> (f32, f32) {
+    let rows = settings.brick_rows.max(1);
+    let columns = settings.brick_columns.max(1);
+    let y = v * rows as f32;
+    let row = y.floor() as i32;
+    let shift = if row % 2 == 1 {
+        settings.brick_offset
+    } else {
+        0.0
+    };
+    let x = u * columns as f32 + shift;
+    let column = (x.floor() as i32).rem_euclid(columns as i32);
+    let (fx, fy) = (x.fract(), y.fract());
+    // Mortar is equally thick both ways on a square texture
+    let mortar_y = settings.mortar * 0.5;
+    let mortar_x = mortar_y * columns as f32 / rows as f32;
+    let in_mortar = fx < mortar_x || fx > 1.0 - mortar_x || fy < mortar_y || fy > 1.0 - mortar_y;
+    if in_mortar {
+        (0.0, 0.0)
+    } else {
+        let shade = 1.0 - settings.brick_variation * random(column, row, settings.seed);
+        (1.0, shade)
+    }
+}
+
+/// Height in [0, 1] and color of the pattern at (`u`, `v`) in [0, 1)
+fn sample(settings: &ProceduralSettings, u: f32, v: f32) -> (f32, [u8; 3]) {
+    let mix = |t: f32| {
+        let channel = |i: usize| {
+            lerp(
+                settings.low_color[i] as f32,
+                settings.high_color[i] as f32,
+                t,
+            )
+        };
+        [channel(0) as u8, channel(1) as u8, channel(2) as u8]
+    };
+    let height = match settings.generator {
+        Generator::Checkerboard => {
+            let cells = settings.scale.max(1.0);
+            ((u * cells).floor() + (v * cells).floor()).rem_euclid(2.0)
+        }
+        Generator::Perlin => fractal(settings, perlin, u, v),
+        Generator::Simplex => fractal(settings, simplex, u, v),
+        Generator::Worley => {
+            let cells = settings.scale.round().max(1.0) as u32;
+            // The nearest point is at most sqrt(2) cells away, usually
+            // within one
+            let distance = worley(u, v, cells, settings.seed).min(1.0);
+            if settings.invert {
+                1.0 - distance
+            } else {
+                distance
+            }
+        }
+        Generator::Gradient => gradient_value(settings, u, v),
+        Generator::Bricks => {
+            let (height, shade) = bricks(settings, u, v);
+            if height == 0.0 {
+                return (0.0, settings.low_color);
+            }
+            let color = settings.high_color.map(|c| (c as f32 * shade) as u8);
+            return (height, color);
+        }
+    };
+    (height, mix(height))
+}
+
+/// Generate the texture of `settings` at `width` by `height` pixels
+pub fn generate(settings: &ProceduralSettings, width: u32, height: u32) -> DecodedImage {
+    let (width, height) = (width.max(1), height.max(1));
+    let mut heights = Vec::with_capacity((width * height) as usize);
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        let v = (y as f32 + 0.5) / height as f32;
+        for x in 0..width {
+            let u = (x as f32 + 0.5) / width as f32;
+            let (value, color) = sample(settings, u, v);
+            heights.push(value);
+            rgba.extend_from_slice(&[color[0], color[1], color[2], 255]);
+        }
+    }
+    if settings.normal_map {
+        rgba = height_to_normal_map(&heights, width, height, settings.normal_strength);
+    }
+    DecodedImage {
+        width,
+        height,
+        rgba,
+    }
+}
+
+/// Generate the texture of `settings` on the global job system
+pub fn spawn_generate(settings: ProceduralSettings, width: u32, height: u32) -> Job<DecodedImage> {
+    JobSystem::global().spawn(settings.generator.name(), move |progress| {
+        progress.set_stage("Generating", 0.0);
+        let image = generate(&settings, width, height);
+        progress.set_stage("Generated", 1.0);
+        image
+    })
+}
+
+/// Size to preview a `width` by `height` texture at, no side above
+/// `max_side`, keeping the aspect ratio
+pub fn preview_size(width: u32, height: u32, max_side: u32) -> (u32, u32) {
+    let largest = width.max(height).max(1);
+    if largest <= max_side {
+        return (width.max(1), height.max(1));
+    }
+    let scale = |side: u32| ((side as u64 * max_side as u64 / largest as u64) as u32).max(1);
+    (scale(width), scale(height))
+}
+
+/// Heights of an RGBA image, from its luminance
+pub fn luminance_heights(rgba: &[u8]) -> Vec<f32> {
+    rgba.chunks_exact(4)
+        .map(|pixel| {
+            (0.2126 * pixel[0] as f32 + 0.7152 * pixel[1] as f32 + 0.0722 * pixel[2] as f32) / 255.0
+        })
+        .collect()
+}
+
+/// Tangent-space normal map of a `width` by `height` height field
+///
+/// Slopes are central differences that wrap at the edges, scaled by
+/// `strength`; +Y points up the image (OpenGL convention). Flat areas come
+/// out as (128, 128, 255).
+pub fn height_to_normal_map(heights: &[f32], width: u32, height: u32, strength: f32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let at = |x: usize, y: usize| heights[y * w + x];
+    let mut rgba = Vec::with_capacity(w * h * 4);
+    for y in 0..h {
+        let (up, down) = ((y + h - 1) % h, (y + 1) % h);
+        for x in 0..w {
+            let (left, right) = ((x + w - 1) % w, (x + 1) % w);
+            let dx = (at(right, y) - at(left, y)) * 0.5 * strength;
+            // Rows go down the image, so the slope up it is negated
+            let dy = (at(x, up) - at(x, down)) * 0.5 * strength;
+            let length = (dx * dx + dy * dy + 1.0).sqrt();
+            let encode = |n: f32| ((n / length * 0.5 + 0.5) * 255.0).round() as u8;
+            rgba.extend_from_slice(&[encode(-dx), encode(-dy), encode(1.0), 255]);
+        }
+    }
+    rgba
+}
+
+/// A generated image uploaded to a GPU texture
+///
+/// The texture can be sampled and copied from: `TEXTURE_BINDING`,
+/// `COPY_SRC` and `COPY_DST`, one mip level.
+pub struct BakedTexture {
+    pub label: String,
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+}
+
+impl BakedTexture {
+    /// Upload `image` into a new `format` texture
+    ///
+    /// Color textures should use `Rgba8UnormSrgb`; normal maps hold
+    /// vectors, not colors, and need `Rgba8Unorm`.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        image: &DecodedImage,
+        label: &str,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let texture = TextureBuilder::texture_2d(image.width, image.height)
+            .with_format(format)
+            .with_usage(
+                wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC
+                    | wgpu::TextureUsages::COPY_DST,
+            )
+            .with_label(label)
+            .build(device);
+        ApiCoverageTracker::global().record(ApiCategory::Queue, "write_texture");
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &image.rgba,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * image.width),
+                rows_per_image: Some(image.height),
+            },
+            texture.size(),
+        );
+        ApiCoverageTracker::global().record(ApiCategory::Texture, "create_view");
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            label: label.to_string(),
+            texture,
+            view,
+        }
+    }
+
+    /// Width and height in pixels
+    pub fn size(&self) -> (u32, u32) {
+        (self.texture.width(), self.texture.height())
+    }
+
+    /// Format the texture was created with
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.texture.format()
+    }
+}
+
+/// Parameter fields of the selected generator; true if any changed
+pub fn settings_ui(ui: &mut egui::Ui, settings: &mut ProceduralSettings) -> bool {
+    let previous = settings.clone();
+    egui::Grid::new("procedural_settings")
+        .num_columns(2)
+        .spacing([10.0, 6.0])
+        .show(ui, |ui| {
+            ui.label("Pattern:");
+            egui::ComboBox::from_id_salt("procedural_generator")
+                .selected_text(settings.generator.name())
+                .show_ui(ui, |ui| {
+                    for generator in Generator::ALL {
+                        ui.selectable_value(&mut settings.generator, generator, generator.name());
+                    }
+                });
+            ui.end_row();
+
+            match settings.generator {
+                Generator::Checkerboard => {
+                    ui.label("Squares:");
+                    ui.add(egui::Slider::new(&mut settings.scale, 1.0..=64.0).step_by(1.0));
+                    ui.end_row();
+                }
+                Generator::Perlin | Generator::Simplex => {
+                    ui.label("Scale:");
+                    ui.add(egui::Slider::new(&mut settings.scale, 0.5..=64.0).logarithmic(true))
+                        .on_hover_text("Noise periods across the image at the first octave");
+                    ui.end_row();
+                    ui.label("Octaves:");
+                    ui.add(egui::Slider::new(&mut settings.octaves, 1..=8));
+                    ui.end_row();
+                    ui.label("Persistence:");
+                    ui.add(egui::Slider::new(&mut settings.persistence, 0.1..=1.0))
+                        .on_hover_text("Amplitude of each octave relative to the one before");
+                    ui.end_row();
+                    ui.label("Lacunarity:");
+                    ui.add(egui::Slider::new(&mut settings.lacunarity, 1.5..=4.0))
+                        .on_hover_text("Frequency of each octave relative to the one before");
+                    ui.end_row();
+                }
+                Generator::Worley => {
+                    ui.label("Cells:");
+                    ui.add(egui::Slider::new(&mut settings.scale, 1.0..=64.0).step_by(1.0));
+                    ui.end_row();
+                    ui.label("Invert:");
+                    ui.checkbox(&mut settings.invert, "Bright cell centers");
+                    ui.end_row();
+                }
+                Generator::Gradient => {
+                    ui.label("Shape:");
+                    egui::ComboBox::from_id_salt("procedural_gradient")
+                        .selected_text(settings.gradient.name())
+                        .show_ui(ui, |ui| {
+                            for kind in GradientKind::ALL {
+                                ui.selectable_value(&mut settings.gradient, kind, kind.name());
+                            }
+                        });
+                    ui.end_row();
+                    if settings.gradient != GradientKind::Radial {
+                        ui.label("Angle:");
+                        ui.add(egui::Slider::new(&mut settings.angle, 0.0..=360.0).suffix("°"));
+                        ui.end_row();
+                    }
+                }
+                Generator::Bricks => {
+                    ui.label("Rows:");
+                    ui.add(egui::Slider::new(&mut settings.brick_rows, 1..=32));
+                    ui.end_row();
+                    ui.label("Columns:");
+                    ui.add(egui::Slider::new(&mut settings.brick_columns, 1..=32));
+                    ui.end_row();
+                    ui.label("Mortar:");
+                    ui.add(egui::Slider::new(&mut settings.mortar, 0.0..=0.5))
+                        .on_hover_text("Thickness as a fraction of a brick's height");
+                    ui.end_row();
+                    ui.label("Row offset:");
+                    ui.add(egui::Slider::new(&mut settings.brick_offset, 0.0..=1.0));
+                    ui.end_row();
+                    ui.label("Shade variation:");
+                    ui.add(egui::Slider::new(&mut settings.brick_variation, 0.0..=1.0));
+                    ui.end_row();
+                }
+            }
+
+            if settings.generator.is_noise()
+                || matches!(settings.generator, Generator::Worley | Generator::Bricks)
+            {
+                ui.label("Seed:");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut settings.seed));
+                    if ui.small_button("🎲").on_hover_text("Random seed").clicked() {
+                        settings.seed = hash(settings.seed as i32, 0, 0x5EED);
+                    }
+                });
+                ui.end_row();
+            }
+
+            let (low, high) = if settings.generator == Generator::Bricks {
+                ("Mortar color:", "Brick color:")
+            } else {
+                ("Low color:", "High color:")
+            };
+            ui.label(low);
+            ui.color_edit_button_srgb(&mut settings.low_color);
+            ui.end_row();
+            ui.label(high);
+            ui.color_edit_button_srgb(&mut settings.high_color);
+            ui.end_row();
+
+            ui.label("Normal map:");
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut settings.normal_map, "From height")
+                    .on_hover_text(
+                        "Output the slopes of the pattern as a tangent-space normal map",
+                    );
+                if settings.normal_map {
+                    ui.add(
+                        egui::Slider::new(&mut settings.normal_strength, 0.5..=64.0)
+                            .logarithmic(true)
+                            .text("strength"),
+                    );
+                }
+            });
+            ui.end_row();
+        });
+    *settings != previous
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noise_is_deterministic_and_bounded() {
+        for generator in [Generator::Perlin, Generator::Simplex, Generator::Worley] {
+            let settings = ProceduralSettings {
+                generator,
+                seed: 7,
+                ..Default::default()
+            };
+            let image = generate(&settings, 32, 32);
+            assert_eq!(image, generate(&settings, 32, 32));
+            // Not flat
+            let red: Vec<u8> = image.rgba.chunks_exact(4).map(|p| p[0]).collect();
+            assert!(red.iter().max() > red.iter().min(), "{:?}", generator);
+
+            let reseeded = ProceduralSettings {
+                seed: 8,
+                ..settings
+            };
+            assert_ne!(image, generate(&reseeded, 32, 32));
+        }
+        for i in 0..1000 {
+            let (x, y) = (i as f32 * 0.173, i as f32 * 0.319);
+            assert!(perlin(x, y, 1).abs() <= 1.5);
+            assert!(simplex(x, y, 1).abs() <= 1.5);
+        }
+        // Lattice points of Perlin noise are zero
+        assert_eq!(perlin(3.0, 5.0, 1), 0.0);
+    }
+
+    #[test]
+    fn test_patterns() {
+        let checker = generate(&ProceduralSettings::default(), 16, 16);
+        assert_eq!(checker.rgba[0..4], [100, 100, 100, 255]);
+        // Eight squares across: the second one starts at pixel 2
+        assert_eq!(checker.rgba[8..12], [200, 200, 200, 255]);
+
+        let gradient = ProceduralSettings {
+            generator: Generator::Gradient,
+            low_color: [0, 0, 0],
+            high_color: [255, 255, 255],
+            ..Default::default()
+        };
+        let image = generate(&gradient, 64, 1);
+        assert!(image.rgba[0] < 8);
+        assert!(image.rgba[63 * 4] > 247);
+
+        let bricks = ProceduralSettings {
+            generator: Generator::Bricks,
+            mortar: 0.3,
+            brick_variation: 0.0,
+            ..Default::default()
+        };
+        let image = generate(&bricks, 64, 64);
+        // Mortar along the top edge, brick in the middle of the first row
+        assert_eq!(image.rgba[0..3], bricks.low_color);
+        assert_eq!(image.rgba[(4 * 64 + 8) * 4..][..3], bricks.high_color);
+    }
+
+    #[test]
+    fn test_preview_size() {
+        assert_eq!(preview_size(256, 128, 512), (256, 128));
+        assert_eq!(preview_size(4096, 1024, 512), (512, 128));
+        assert_eq!(preview_size(1, 8192, 512), (1, 512));
+    }
+
+    #[test]
+    fn test_worley_wraps_around() {
+        // Opposite edges of the texture are neighbors
+        let left = worley(0.0001, 0.3, 4, 3);
+        let right = worley(0.9999, 0.3, 4, 3);
+        assert!((left - right).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_normal_map_from_height() {
+        let flat = height_to_normal_map(&[0.5; 9], 3, 3, 8.0);
+        assert!(flat.chunks_exact(4).all(|n| n == [128, 128, 255, 255]));
+
+        // Height rising to the right tilts normals to the left
+        let ramp: Vec<f32> = (0..4).flat_map(|_| [0.0, 0.25, 0.5, 0.75]).collect();
+        let normals = height_to_normal_map(&ramp, 4, 4, 4.0);
+        let middle = &normals[(4 + 1) * 4..][..4];
+        assert!(middle[0] < 128);
+        assert_eq!(middle[1], 128);
+
+        let heights = luminance_heights(&[255, 255, 255, 255, 0, 0, 0, 255]);
+        assert!((heights[0] - 1.0).abs() < 1e-5);
+        assert_eq!(heights[1], 0.0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Starting templates for new projects
+//!
+//! Each template is a serialized [`PlaygroundState`] shipped with the crate
+//! in the `templates` directory. Templates set every panel they use, with a
+//! shader whose bindings match the bind group, so a new project works
+//! before anything is edited. Unlike [presets](crate::preset), which
+//! demonstrate a technique, templates are meant as the first step of a
+//! project.
+
+use crate::state::PlaygroundState;
+
+/// Template a new project starts from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProjectTemplate {
+    /// Full-screen triangle in a single color
+    #[default]
+    Blank,
+    /// Texture drawn on a quad through a sampler
+    TexturedQuad,
+    /// Compute shader on a storage buffer, without rendering
+    ComputeOnly,
+    /// Chain of full-screen effects applied to a texture
+    PostProcessing,
+    /// Particles simulated by a compute shader and drawn every frame
+    ParticleSystem,
+}
+
+impl ProjectTemplate {
+    /// Every template, in the order the wizard lists them
+    pub const ALL: [ProjectTemplate; 5] = [
+        ProjectTemplate::Blank,
+        ProjectTemplate::TexturedQuad,
+        ProjectTemplate::ComputeOnly,
+        ProjectTemplate::PostProcessing,
+        ProjectTemplate::ParticleSystem,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            ProjectTemplate::Blank => "Blank",
+            ProjectTemplate::TexturedQuad => "Textured Quad",
+            ProjectTemplate::ComputeOnly => "Compute Only",
+            ProjectTemplate::PostProcessing => "Post-Processing Chain",
+            ProjectTemplate::ParticleSystem => "Particle System",
+        }
+    }
+
+    /// Stable identifier, the name of the template file
+    pub fn id(self) -> &'static str {
+        match self {
+            ProjectTemplate::Blank => "blank",
+            ProjectTemplate::TexturedQuad => "textured_quad",
+            ProjectTemplate::ComputeOnly => "compute_only",
+            ProjectTemplate::PostProcessing => "post_processing",
+            ProjectTemplate::ParticleSystem => "particle_system",
+        }
+    }
+
+    /// Template with the given identifier
+    pub fn from_id(id: &str) -> Option<ProjectTemplate> {
+        ProjectTemplate::ALL
+            .into_iter()
+            .find(|template| template.id() == id)
+    }
+
+    /// What the template sets up
+    pub fn description(self) -> &'static str {
+        match self {
+            ProjectTemplate::Blank => {
+                "A render pipeline drawing a full-screen triangle in a single color, \
+                 with no bindings. Start here to write a shader from scratch."
+            }
+            ProjectTemplate::TexturedQuad => {
+                "A checkerboard texture and a repeating sampler bound to a fragment \
+                 shader that draws the texture on a quad in the middle of the view."
+            }
+            ProjectTemplate::ComputeOnly => {
+                "A compute pipeline that squares every value of a storage buffer. \
+                 There is no render pipeline; export it as a compute project to run \
+                 the dispatch and print the results."
+            }
+            ProjectTemplate::PostProcessing => {
+                "A full-screen pass over a scene texture that runs it through a chain \
+                 of effects: chromatic aberration, tone mapping, vignette and \
+                 scanlines, each a function that is easy to reorder or remove."
+            }
+            ProjectTemplate::ParticleSystem => {
+                "A storage buffer of particles that a compute shader moves under \
+                 gravity and respawns at an emitter, drawn as glowing dots by the \
+                 fragment shader reading the same buffer."
+            }
+        }
+    }
+
+    /// Serialized state of the template
+    fn json(self) -> &'static str {
+        match self {
+            ProjectTemplate::Blank => include_str!("../templates/blank.json"),
+            ProjectTemplate::TexturedQuad => include_str!("../templates/textured_quad.json"),
+            ProjectTemplate::ComputeOnly => include_str!("../templates/compute_only.json"),
+            ProjectTemplate::PostProcessing => include_str!("../templates/post_processing.json"),
+            ProjectTemplate::ParticleSystem => include_str!("../templates/particle_system.json"),
+        }
+    }
+
+    /// Playground state a project from this template starts with
+    pub fn state(self) -> PlaygroundState {
+        PlaygroundState::from_json(self.json())
+            .unwrap_or_else(|e| panic!("The {} template is invalid: {}", self.name(), e))
+    }
+}
@@ -0,0 +1,265 @@
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+use std::sync::Arc;
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+use std::time::Duration;
+
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+use crate::cpu_profiler::{CpuFrame, CpuScope};
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+use crate::cpu_profiler_panel::{render_flame_graph, render_stats};
+
+/// Frames averaged in the per-scope table
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+const STATS_FRAMES: usize = 60;
+
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+fn nanos(ns: puffin::NanoSecond) -> Duration {
+    Duration::from_nanos(ns.max(0) as u64)
+}
+
+/// The scopes of one puffin frame, per thread, in the form the CPU
+/// profiler's flame graph draws
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+fn convert_frame(
+    frame: &puffin::FrameData,
+    collection: &puffin::ScopeCollection,
+) -> Vec<(String, CpuFrame)> {
+    let Ok(unpacked) = frame.unpacked() else {
+        return Vec::new();
+    };
+    let (start, end) = unpacked.meta.range_ns;
+    unpacked
+        .thread_streams
+        .iter()
+        .map(|(thread, info)| {
+            let mut scopes = Vec::new();
+            collect_scopes(&info.stream, 0, 0, start, collection, &mut scopes);
+            let frame = CpuFrame {
+                frame: unpacked.meta.frame_index,
+                duration: nanos(end - start),
+                scopes,
+            };
+            (thread.name.clone(), frame)
+        })
+        .collect()
+}
+
+/// Append the scopes starting at `offset` in `stream` and their children,
+/// depth first
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+fn collect_scopes(
+    stream: &puffin::Stream,
+    offset: u64,
+    depth: usize,
+    frame_start: puffin::NanoSecond,
+    collection: &puffin::ScopeCollection,
+    scopes: &mut Vec<CpuScope>,
+) {
+    let Ok(reader) = puffin::Reader::with_offset(stream, offset) else {
+        return;
+    };
+    for scope in reader.flatten() {
+        let name = collection
+            .fetch_by_id(&scope.id)
+            .map(|details| details.name().to_string())
+            .unwrap_or_else(|| format!("{:?}", scope.id));
+        scopes.push(CpuScope {
+            name: name.into(),
+            depth,
+            start: nanos(scope.record.start_ns - frame_start),
+            duration: nanos(scope.record.duration_ns),
+        });
+        collect_scopes(
+            stream,
+            scope.child_begin_position,
+            depth + 1,
+            frame_start,
+            collection,
+            scopes,
+        );
+    }
+}
+
+/// The frames of `thread` among converted frames
+#[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+fn thread_frames(frames: &[Vec<(String, CpuFrame)>], thread: &str) -> Vec<CpuFrame> {
+    frames
+        .iter()
+        .filter_map(|threads| {
+            threads
+                .iter()
+                .find(|(name, _)| name == thread)
+                .map(|(_, frame)| frame.clone())
+        })
+        .collect()
+}
+
+/// UI panel showing the spans sent to puffin, from every thread, without
+/// an external viewer
+pub struct PuffinViewerPanel {
+    /// Receives each finished puffin frame
+    #[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+    view: puffin::GlobalFrameView,
+    /// Frames shown while paused, oldest first
+    #[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+    paused: Option<Vec<Arc<puffin::FrameData>>>,
+    /// Thread whose scopes are shown; the first one recorded when `None`
+    #[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+    thread: Option<String>,
+}
+
+impl Default for PuffinViewerPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PuffinViewerPanel {
+    /// Create the panel; with the `puffin` feature this turns puffin scopes
+    /// on
+    pub fn new() -> Self {
+        #[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+        {
+            puffin::set_scopes_on(true);
+            Self {
+                view: puffin::GlobalFrameView::default(),
+                paused: None,
+                thread: None,
+            }
+        }
+        #[cfg(not(all(feature = "puffin", not(target_arch = "wasm32"))))]
+        {
+            Self {}
+        }
+    }
+
+    /// Render the panel
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🔬 Puffin Viewer");
+        ui.separator();
+        ui.label(
+            "Spans from the profiling crate, which marks pipeline creation, texture uploads, \
+             preview renders, readbacks and the render loop's stages, on every thread.",
+        );
+        ui.add_space(10.0);
+
+        #[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+        self.render_frames(ui);
+        #[cfg(not(all(feature = "puffin", not(target_arch = "wasm32"))))]
+        {
+            ui.label("Build with the puffin feature to record and show the spans here:");
+            ui.code("cargo run --release --features puffin");
+            ui.label(
+                egui::RichText::new(
+                    "With the tracy feature instead, the same spans go to the Tracy profiler.",
+                )
+                .small()
+                .weak(),
+            );
+        }
+    }
+
+    #[cfg(all(feature = "puffin", not(target_arch = "wasm32")))]
+    fn render_frames(&mut self, ui: &mut egui::Ui) {
+        let view = self.view.lock();
+        let recent: Vec<Arc<puffin::FrameData>> = match &self.paused {
+            Some(frames) => frames.clone(),
+            None => view.recent_frames().cloned().collect(),
+        };
+
+        ui.horizontal(|ui| {
+            if self.paused.is_some() {
+                if ui.button("▶ Resume").clicked() {
+                    self.paused = None;
+                }
+            } else if ui.button("⏸ Pause").clicked() {
+                self.paused = Some(recent.clone());
+            }
+            ui.label(format!("{} frames", recent.len()));
+        });
+
+        let converted: Vec<Vec<(String, CpuFrame)>> = recent
+            .iter()
+            .rev()
+            .take(STATS_FRAMES)
+            .rev()
+            .map(|frame| convert_frame(frame, view.scope_collection()))
+            .collect();
+        drop(view);
+        let Some(latest) = converted.last() else {
+            ui.label("No frames recorded yet.");
+            return;
+        };
+
+        let threads: Vec<String> = latest.iter().map(|(name, _)| name.clone()).collect();
+        let thread = match &self.thread {
+            Some(thread) if threads.contains(thread) => thread.clone(),
+            _ => threads.first().cloned().unwrap_or_default(),
+        };
+        let mut selected = thread.clone();
+        egui::ComboBox::from_label("Thread")
+            .selected_text(selected.as_str())
+            .show_ui(ui, |ui| {
+                for name in &threads {
+                    ui.selectable_value(&mut selected, name.clone(), name.as_str());
+                }
+            });
+        if selected != thread {
+            self.thread = Some(selected.clone());
+        }
+        ui.add_space(5.0);
+
+        let frames = thread_frames(&converted, &selected);
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if let Some(frame) = frames.last() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Frame {} ({:.2} ms)",
+                        frame.frame,
+                        frame.duration.as_secs_f64() * 1000.0
+                    ))
+                    .strong(),
+                );
+                render_flame_graph(ui, frame);
+            }
+            ui.add_space(10.0);
+            ui.label(egui::RichText::new(format!("Mean over {} frames", frames.len())).strong());
+            render_stats(ui, &frames);
+        });
+    }
+}
+
+#[cfg(all(test, feature = "puffin", not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    fn frame(number: u64, scope: &'static str) -> CpuFrame {
+        CpuFrame {
+            frame: number,
+            duration: Duration::from_millis(1),
+            scopes: vec![CpuScope {
+                name: scope.into(),
+                depth: 0,
+                start: Duration::ZERO,
+                duration: Duration::from_millis(1),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_thread_frames() {
+        let converted = vec![
+            vec![
+                ("main".to_string(), frame(0, "Record")),
+                ("worker".to_string(), frame(0, "Decode")),
+            ],
+            vec![("main".to_string(), frame(1, "Record"))],
+        ];
+        let main = thread_frames(&converted, "main");
+        assert_eq!(main.len(), 2);
+        assert_eq!(main[1].frame, 1);
+        let worker = thread_frames(&converted, "worker");
+        assert_eq!(worker.len(), 1);
+        assert_eq!(worker[0].scopes[0].name, "Decode");
+    }
+}
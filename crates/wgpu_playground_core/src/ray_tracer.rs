@@ -0,0 +1,1546 @@
+//! Progressive path tracer running in a compute shader
+//!
+//! A triangle mesh, usually loaded from glTF, is sorted into a bounding
+//! volume hierarchy on the CPU and uploaded as storage buffers: vertices,
+//! triangles with their material, the BVH nodes and the materials. Every
+//! frame a compute pass traces a few paths per pixel through it, walking
+//! the BVH with a small stack, and blends them into the running mean of
+//! all samples so far. The mean lives in a pair of `rgba32float` storage
+//! textures that swap roles each frame, since a storage texture of that
+//! format can't be read and written in the same pass. A fullscreen
+//! triangle tone maps the latest mean into the target.
+//!
+//! Moving the camera or changing a setting starts the mean over, so the
+//! image is noisy while it moves and converges while it stands still.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{mat4_inverse, Mat4, MAT4_IDENTITY};
+use crate::model_loader::{self, ModelData, ModelLoadError};
+use crate::upload_belt;
+use std::path::Path;
+
+/// Model the example starts with, in the models directory
+pub const DEFAULT_MODEL: &str = "ray_tracer_scene.glb";
+
+/// Most triangles a BVH leaf keeps before splitting stops paying off
+pub const MAX_LEAF_TRIANGLES: usize = 8;
+
+/// Depth of the traversal stack in the shader
+pub const MAX_BVH_DEPTH: u32 = 48;
+
+/// Most bounces a path takes
+pub const MAX_BOUNCES: u32 = 12;
+
+const WORKGROUP_SIZE: u32 = 8;
+
+/// Bins per axis tried for each split
+const SAH_BINS: usize = 12;
+
+const ACCUMULATION_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba32Float;
+
+pub const RAY_TRACER_SHADER: &str = r#"// Compute Ray Tracer Example
+// One invocation per pixel traces `samples` paths through the BVH and
+// blends them into the mean of the previous frames
+
+struct Uniforms {
+    inverse_view_proj: mat4x4<f32>,
+    eye: vec3<f32>,
+    // Samples in the mean so far
+    accumulated: u32,
+    sun_direction: vec3<f32>,
+    max_bounces: u32,
+    samples: u32,
+    emission_strength: f32,
+    show_bvh: u32,
+    node_count: u32,
+    size: vec2<u32>,
+    _padding: vec2<u32>,
+}
+
+struct Vertex {
+    position: vec3<f32>,
+    normal: vec3<f32>,
+}
+
+struct Material {
+    base_color: vec3<f32>,
+    metallic: f32,
+    emission: vec3<f32>,
+    roughness: f32,
+}
+
+// Leaves have triangles `left_first..left_first + count`; inner nodes
+// have no triangles and their children at `left_first` and the one after
+struct Node {
+    min: vec3<f32>,
+    left_first: u32,
+    max: vec3<f32>,
+    count: u32,
+}
+
+struct Hit {
+    distance: f32,
+    triangle: u32,
+    barycentric: vec2<f32>,
+    // Nodes visited, for the heatmap
+    visits: u32,
+}
+
+const MAX_BVH_DEPTH: u32 = 48u;
+const NO_HIT: f32 = 1e30;
+const PI: f32 = 3.14159265;
+const SUN_COLOR: vec3<f32> = vec3<f32>(1.8, 1.7, 1.5);
+// Half the angle the sun covers, for soft shadow edges
+const SUN_ANGLE: f32 = 0.03;
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var<storage, read> vertices: array<Vertex>;
+// Three vertex indices and the material
+@group(0) @binding(2) var<storage, read> triangles: array<vec4<u32>>;
+@group(0) @binding(3) var<storage, read> nodes: array<Node>;
+@group(0) @binding(4) var<storage, read> materials: array<Material>;
+@group(0) @binding(5) var previous: texture_2d<f32>;
+@group(0) @binding(6) var accumulation: texture_storage_2d<rgba32float, write>;
+
+var<private> rng_state: u32;
+
+fn random() -> f32 {
+    rng_state = rng_state * 747796405u + 2891336453u;
+    let word = ((rng_state >> ((rng_state >> 28u) + 4u)) ^ rng_state) * 277803737u;
+    // 24 bits, so the result stays below 1
+    return f32(((word >> 22u) ^ word) >> 8u) / 16777216.0;
+}
+
+// Directions around `normal`, more of them near it, like Lambert's cosine
+fn cosine_sample(normal: vec3<f32>) -> vec3<f32> {
+    let angle = 2.0 * PI * random();
+    let r = sqrt(random());
+    let helper = select(vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 1.0, 0.0), abs(normal.x) > 0.9);
+    let tangent = normalize(cross(helper, normal));
+    let bitangent = cross(normal, tangent);
+    return normalize(tangent * (r * cos(angle)) + bitangent * (r * sin(angle)) + normal * sqrt(1.0 - r * r));
+}
+
+fn random_in_sphere() -> vec3<f32> {
+    let z = 2.0 * random() - 1.0;
+    let angle = 2.0 * PI * random();
+    let r = sqrt(1.0 - z * z);
+    return vec3<f32>(r * cos(angle), r * sin(angle), z) * pow(random(), 1.0 / 3.0);
+}
+
+// Distance along the ray to the box, or NO_HIT if it misses or is
+// further than `limit`
+fn intersect_box(origin: vec3<f32>, inverse_direction: vec3<f32>, low: vec3<f32>, high: vec3<f32>, limit: f32) -> f32 {
+    let t0 = (low - origin) * inverse_direction;
+    let t1 = (high - origin) * inverse_direction;
+    let near = max(max(min(t0.x, t1.x), min(t0.y, t1.y)), min(t0.z, t1.z));
+    let far = min(min(max(t0.x, t1.x), max(t0.y, t1.y)), max(t0.z, t1.z));
+    if far >= max(near, 0.0) && near < limit {
+        return near;
+    }
+    return NO_HIT;
+}
+
+// Möller-Trumbore; the distance and barycentric coordinates of the hit
+fn intersect_triangle(origin: vec3<f32>, direction: vec3<f32>, triangle: vec4<u32>) -> vec3<f32> {
+    let v0 = vertices[triangle.x].position;
+    let edge1 = vertices[triangle.y].position - v0;
+    let edge2 = vertices[triangle.z].position - v0;
+    let p = cross(direction, edge2);
+    let determinant = dot(edge1, p);
+    if abs(determinant) < 1e-9 {
+        return vec3<f32>(NO_HIT, 0.0, 0.0);
+    }
+    let inverse = 1.0 / determinant;
+    let s = origin - v0;
+    let u = dot(s, p) * inverse;
+    let q = cross(s, edge1);
+    let v = dot(direction, q) * inverse;
+    let t = dot(edge2, q) * inverse;
+    if u < 0.0 || v < 0.0 || u + v > 1.0 || t <= 0.0 {
+        return vec3<f32>(NO_HIT, 0.0, 0.0);
+    }
+    return vec3<f32>(t, u, v);
+}
+
+// Closest hit: visit the nearer child first and keep the further one on
+// the stack, skipping boxes beyond the closest triangle found so far
+fn trace(origin: vec3<f32>, direction: vec3<f32>) -> Hit {
+    var hit = Hit(NO_HIT, 0u, vec2<f32>(0.0), 0u);
+    if uniforms.node_count == 0u {
+        return hit;
+    }
+    let inverse_direction = 1.0 / direction;
+    var stack: array<u32, MAX_BVH_DEPTH>;
+    var depth = 0u;
+    var index = 0u;
+    if intersect_box(origin, inverse_direction, nodes[0].min, nodes[0].max, NO_HIT) == NO_HIT {
+        return hit;
+    }
+    loop {
+        let node = nodes[index];
+        hit.visits++;
+        if node.count > 0u {
+            for (var i = node.left_first; i < node.left_first + node.count; i++) {
+                let candidate = intersect_triangle(origin, direction, triangles[i]);
+                if candidate.x < hit.distance {
+                    hit.distance = candidate.x;
+                    hit.triangle = i;
+                    hit.barycentric = candidate.yz;
+                }
+            }
+        } else {
+            var near = node.left_first;
+            var far = node.left_first + 1u;
+            var near_distance = intersect_box(origin, inverse_direction, nodes[near].min, nodes[near].max, hit.distance);
+            var far_distance = intersect_box(origin, inverse_direction, nodes[far].min, nodes[far].max, hit.distance);
+            if far_distance < near_distance {
+                let swapped = near;
+                near = far;
+                far = swapped;
+                let swapped_distance = near_distance;
+                near_distance = far_distance;
+                far_distance = swapped_distance;
+            }
+            if near_distance != NO_HIT {
+                if far_distance != NO_HIT && depth < MAX_BVH_DEPTH {
+                    stack[depth] = far;
+                    depth++;
+                }
+                index = near;
+                continue;
+            }
+        }
+        if depth == 0u {
+            break;
+        }
+        depth--;
+        index = stack[depth];
+    }
+    return hit;
+}
+
+fn sky(direction: vec3<f32>) -> vec3<f32> {
+    let up = clamp(direction.y, -1.0, 1.0);
+    let horizon = vec3<f32>(0.75, 0.8, 0.88);
+    let zenith = vec3<f32>(0.22, 0.42, 0.85);
+    if up < 0.0 {
+        return horizon * 0.3;
+    }
+    return mix(horizon, zenith, sqrt(up));
+}
+
+fn heat(t: f32) -> vec3<f32> {
+    let x = clamp(t, 0.0, 1.0);
+    return clamp(vec3<f32>(2.0 * x - 0.5, 1.0 - abs(2.0 * x - 1.0), 1.5 - 2.0 * x), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+fn radiance(start: vec3<f32>, start_direction: vec3<f32>) -> vec3<f32> {
+    var origin = start;
+    var direction = start_direction;
+    var throughput = vec3<f32>(1.0);
+    var color = vec3<f32>(0.0);
+    for (var bounce = 0u; bounce <= uniforms.max_bounces; bounce++) {
+        let hit = trace(origin, direction);
+        if hit.distance == NO_HIT {
+            color += throughput * sky(direction);
+            break;
+        }
+        let triangle = triangles[hit.triangle];
+        let material = materials[triangle.w];
+        let a = vertices[triangle.x];
+        let b = vertices[triangle.y];
+        let c = vertices[triangle.z];
+        let weights = vec3<f32>(1.0 - hit.barycentric.x - hit.barycentric.y, hit.barycentric);
+        var normal = normalize(a.normal * weights.x + b.normal * weights.y + c.normal * weights.z);
+        var geometric = normalize(cross(b.position - a.position, c.position - a.position));
+        // Both sides of a triangle are surfaces
+        if dot(geometric, direction) > 0.0 {
+            geometric = -geometric;
+        }
+        if dot(normal, geometric) < 0.0 {
+            normal = -normal;
+        }
+        color += throughput * material.emission * uniforms.emission_strength;
+        if bounce == uniforms.max_bounces {
+            break;
+        }
+
+        let position = origin + direction * hit.distance;
+        origin = position + geometric * max(1e-4, 1e-4 * hit.distance);
+        if random() < material.metallic {
+            // A mirror blurred by the roughness
+            direction = normalize(reflect(direction, normal) + material.roughness * random_in_sphere());
+            if dot(direction, geometric) <= 0.0 {
+                break;
+            }
+        } else {
+            // Sunlight straight from a point of the sun's disk
+            let sun = normalize(uniforms.sun_direction + SUN_ANGLE * random_in_sphere());
+            let facing = dot(normal, sun);
+            if facing > 0.0 && trace(origin, sun).distance == NO_HIT {
+                color += throughput * material.base_color * SUN_COLOR * facing;
+            }
+            direction = cosine_sample(normal);
+        }
+        throughput *= material.base_color;
+    }
+    return color;
+}
+
+@compute @workgroup_size(8, 8)
+fn trace_paths(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= uniforms.size) {
+        return;
+    }
+    rng_state = (id.y * uniforms.size.x + id.x) * 9781u + uniforms.accumulated * 6271u + 1u;
+
+    var sum = vec3<f32>(0.0);
+    for (var sample = 0u; sample < uniforms.samples; sample++) {
+        // Jitter within the pixel for antialiasing
+        let pixel = vec2<f32>(id.xy) + vec2<f32>(random(), random());
+        let ndc = vec2<f32>(pixel.x / f32(uniforms.size.x) * 2.0 - 1.0, 1.0 - pixel.y / f32(uniforms.size.y) * 2.0);
+        let far = uniforms.inverse_view_proj * vec4<f32>(ndc, 1.0, 1.0);
+        let direction = normalize(far.xyz / far.w - uniforms.eye);
+        if uniforms.show_bvh == 1u {
+            sum += heat(f32(trace(uniforms.eye, direction).visits) / 64.0);
+        } else {
+            sum += radiance(uniforms.eye, direction);
+        }
+    }
+
+    var mean = sum / f32(uniforms.samples);
+    if uniforms.accumulated > 0u {
+        let total = f32(uniforms.accumulated + uniforms.samples);
+        let before = textureLoad(previous, id.xy, 0).rgb;
+        mean = before + (sum - before * f32(uniforms.samples)) / total;
+    }
+    textureStore(accumulation, id.xy, vec4<f32>(mean, 1.0));
+}
+"#;
+
+pub const RAY_TRACER_DISPLAY_SHADER: &str = r#"// Compute Ray Tracer display: tone map the mean of the samples
+
+@group(0) @binding(0) var accumulation: texture_2d<f32>;
+
+const EXPOSURE: f32 = 0.6;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// Narkowicz's fit of the ACES filmic curve
+fn aces(x: vec3<f32>) -> vec3<f32> {
+    return clamp((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let mean = textureLoad(accumulation, vec2<u32>(position.xy), 0).rgb;
+    return vec4<f32>(aces(mean * EXPOSURE), 1.0);
+}
+"#;
+
+/// Why a model can't be traced
+#[derive(Debug)]
+pub enum RayTracerError {
+    /// The model file couldn't be loaded
+    Model(ModelLoadError),
+    /// The model has no triangles
+    EmptyModel,
+}
+
+impl std::fmt::Display for RayTracerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RayTracerError::Model(err) => write!(f, "Failed to load the model: {}", err),
+            RayTracerError::EmptyModel => write!(f, "The model has no triangles"),
+        }
+    }
+}
+
+impl std::error::Error for RayTracerError {}
+
+impl From<ModelLoadError> for RayTracerError {
+    fn from(err: ModelLoadError) -> Self {
+        RayTracerError::Model(err)
+    }
+}
+
+/// Surface of the triangles the path tracer understands
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TracerMaterial {
+    pub base_color: [f32; 3],
+    /// Chance of a mirror bounce instead of a diffuse one
+    pub metallic: f32,
+    /// Blur of the mirror bounces
+    pub roughness: f32,
+    /// Light given off, scaled by [`TracerSettings::emission_strength`]
+    pub emission: [f32; 3],
+}
+
+impl Default for TracerMaterial {
+    fn default() -> Self {
+        Self {
+            base_color: [0.8; 3],
+            metallic: 0.0,
+            roughness: 1.0,
+            emission: [0.0; 3],
+        }
+    }
+}
+
+/// All triangles of a model in one list, ready to be sorted into a BVH
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TracerMesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// Three vertex indices and the material index
+    pub triangles: Vec<[u32; 4]>,
+    pub materials: Vec<TracerMaterial>,
+}
+
+impl TracerMesh {
+    /// Merge the meshes of a model; primitives without a material get the
+    /// default one, appended after the model's
+    pub fn from_model(model: &ModelData) -> Self {
+        let mut materials: Vec<TracerMaterial> = model
+            .materials
+            .iter()
+            .map(|material| TracerMaterial {
+                base_color: [
+                    material.diffuse_color[0],
+                    material.diffuse_color[1],
+                    material.diffuse_color[2],
+                ],
+                metallic: material.metallic,
+                roughness: material.roughness,
+                emission: material.emissive,
+            })
+            .collect();
+        let fallback = materials.len() as u32;
+        let mut mesh = Self::default();
+        for part in &model.meshes {
+            let base = mesh.positions.len() as u32;
+            let material = part
+                .material_index
+                .filter(|&index| index < materials.len())
+                .map_or(fallback, |index| index as u32);
+            mesh.positions
+                .extend(part.vertices.iter().map(|vertex| vertex.position));
+            mesh.normals
+                .extend(part.vertices.iter().map(|vertex| vertex.normal));
+            mesh.triangles.extend(
+                part.indices
+                    .chunks_exact(3)
+                    .map(|t| [base + t[0], base + t[1], base + t[2], material]),
+            );
+        }
+        if mesh.triangles.iter().any(|t| t[3] == fallback) {
+            materials.push(TracerMaterial::default());
+        }
+        mesh.materials = materials;
+        mesh
+    }
+
+    /// Load a model file and merge its meshes
+    pub fn load(path: &Path) -> Result<Self, RayTracerError> {
+        let mesh = Self::from_model(&model_loader::load_model_from_file(path)?);
+        if mesh.triangles.is_empty() {
+            return Err(RayTracerError::EmptyModel);
+        }
+        Ok(mesh)
+    }
+
+    /// Corners of the box around all vertices
+    pub fn bounds(&self) -> ([f32; 3], [f32; 3]) {
+        let mut bounds = Aabb::EMPTY;
+        for &position in &self.positions {
+            bounds.grow(position);
+        }
+        (bounds.min, bounds.max)
+    }
+
+    fn corners(&self, triangle: usize) -> [[f32; 3]; 3] {
+        let [a, b, c, _] = self.triangles[triangle];
+        [a, b, c].map(|index| self.positions[index as usize])
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Aabb {
+    min: [f32; 3],
+    max: [f32; 3],
+}
+
+impl Aabb {
+    const EMPTY: Aabb = Aabb {
+        min: [f32::INFINITY; 3],
+        max: [f32::NEG_INFINITY; 3],
+    };
+
+    fn grow(&mut self, point: [f32; 3]) {
+        for (axis, value) in point.into_iter().enumerate() {
+            self.min[axis] = self.min[axis].min(value);
+            self.max[axis] = self.max[axis].max(value);
+        }
+    }
+
+    fn merge(&mut self, other: &Aabb) {
+        self.grow(other.min);
+        self.grow(other.max);
+    }
+
+    /// Half the surface area, all the SAH needs; zero when empty
+    fn half_area(&self) -> f32 {
+        let [x, y, z] = [0, 1, 2].map(|axis| (self.max[axis] - self.min[axis]).max(0.0));
+        x * y + y * z + z * x
+    }
+}
+
+/// Node of a [`Bvh`], laid out like the shader's
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct BvhNode {
+    pub min: [f32; 3],
+    /// First triangle of a leaf, or the left child of an inner node
+    pub left_first: u32,
+    pub max: [f32; 3],
+    /// Triangles of a leaf, zero for inner nodes
+    pub count: u32,
+}
+
+impl BvhNode {
+    pub fn is_leaf(&self) -> bool {
+        self.count > 0
+    }
+}
+
+/// Bounding volume hierarchy over the triangles of a [`TracerMesh`]
+///
+/// Built top down, each node split where the surface area heuristic puts
+/// it among a few evenly spaced candidate planes along each axis, until
+/// splitting costs more than testing the triangles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bvh {
+    /// The root first; children of a node are next to each other
+    pub nodes: Vec<BvhNode>,
+    /// Mesh triangle of every leaf slot, in leaf order
+    pub order: Vec<u32>,
+}
+
+impl Bvh {
+    pub fn build(mesh: &TracerMesh) -> Self {
+        let count = mesh.triangles.len();
+        if count == 0 {
+            return Self::default();
+        }
+        let mut bounds = Vec::with_capacity(count);
+        let mut centroids = Vec::with_capacity(count);
+        for triangle in 0..count {
+            let mut aabb = Aabb::EMPTY;
+            let corners = mesh.corners(triangle);
+            for corner in corners {
+                aabb.grow(corner);
+            }
+            bounds.push(aabb);
+            centroids.push(
+                [0, 1, 2]
+                    .map(|axis| (corners[0][axis] + corners[1][axis] + corners[2][axis]) / 3.0),
+            );
+        }
+
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(2 * count),
+            order: (0..count as u32).collect(),
+        };
+        bvh.nodes.push(BvhNode {
+            min: [0.0; 3],
+            left_first: 0,
+            max: [0.0; 3],
+            count: count as u32,
+        });
+        let mut pending = vec![0];
+        while let Some(index) = pending.pop() {
+            let node = &mut bvh.nodes[index];
+            let first = node.left_first as usize;
+            let slots = first..first + node.count as usize;
+            let mut node_bounds = Aabb::EMPTY;
+            for &triangle in &bvh.order[slots.clone()] {
+                node_bounds.merge(&bounds[triangle as usize]);
+            }
+            node.min = node_bounds.min;
+            node.max = node_bounds.max;
+
+            let Some((axis, plane)) =
+                best_split(&bvh.order[slots.clone()], &bounds, &centroids, &node_bounds)
+            else {
+                continue;
+            };
+            // Partition the slots so the left child's triangles come first
+            let order = &mut bvh.order[slots.clone()];
+            let mut left = 0;
+            for i in 0..order.len() {
+                if centroids[order[i] as usize][axis] < plane {
+                    order.swap(i, left);
+                    left += 1;
+                }
+            }
+            if left == 0 || left == order.len() {
+                continue;
+            }
+
+            let child = bvh.nodes.len();
+            bvh.nodes[index].left_first = child as u32;
+            bvh.nodes[index].count = 0;
+            for (first, count) in [(first, left), (first + left, slots.len() - left)] {
+                bvh.nodes.push(BvhNode {
+                    min: [0.0; 3],
+                    left_first: first as u32,
+                    max: [0.0; 3],
+                    count: count as u32,
+                });
+            }
+            pending.extend([child, child + 1]);
+        }
+        bvh
+    }
+
+    /// Levels from the root to the deepest leaf
+    pub fn depth(&self) -> u32 {
+        fn depth_of(nodes: &[BvhNode], index: usize) -> u32 {
+            let node = &nodes[index];
+            if node.is_leaf() {
+                1
+            } else {
+                let left = node.left_first as usize;
+                1 + depth_of(nodes, left).max(depth_of(nodes, left + 1))
+            }
+        }
+        if self.nodes.is_empty() {
+            0
+        } else {
+            depth_of(&self.nodes, 0)
+        }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.nodes.iter().filter(|node| node.is_leaf()).count()
+    }
+
+    /// Closest triangle hit by the ray, with its distance; the CPU mirror of
+    /// the shader's traversal
+    pub fn intersect(
+        &self,
+        mesh: &TracerMesh,
+        origin: [f32; 3],
+        direction: [f32; 3],
+    ) -> Option<(f32, usize)> {
+        let mut closest: Option<(f32, usize)> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let limit = closest.map_or(f32::INFINITY, |(t, _)| t);
+            if !ray_hits_box(origin, direction, node, limit) {
+                continue;
+            }
+            if node.is_leaf() {
+                let first = node.left_first as usize;
+                for &triangle in &self.order[first..first + node.count as usize] {
+                    let triangle = triangle as usize;
+                    if let Some(t) = ray_triangle(origin, direction, mesh.corners(triangle)) {
+                        if t < limit && closest.is_none_or(|(best, _)| t < best) {
+                            closest = Some((t, triangle));
+                        }
+                    }
+                }
+            } else {
+                stack.push(node.left_first as usize);
+                stack.push(node.left_first as usize + 1);
+            }
+        }
+        closest
+    }
+}
+
+/// Axis and position of the cheapest split of the triangles in `order`, or
+/// `None` if keeping them in one leaf is cheaper
+fn best_split(
+    order: &[u32],
+    bounds: &[Aabb],
+    centroids: &[[f32; 3]],
+    node_bounds: &Aabb,
+) -> Option<(usize, f32)> {
+    if order.len() <= 2 {
+        return None;
+    }
+    let mut centroid_bounds = Aabb::EMPTY;
+    for &triangle in order {
+        centroid_bounds.grow(centroids[triangle as usize]);
+    }
+
+    let mut best: Option<(f32, usize, f32)> = None;
+    let axes = centroid_bounds.min.into_iter().zip(centroid_bounds.max);
+    for (axis, (low, high)) in axes.enumerate() {
+        let extent = high - low;
+        if extent <= 0.0 {
+            continue;
+        }
+        let scale = SAH_BINS as f32 / extent;
+        let bin_of = |triangle: u32| {
+            (((centroids[triangle as usize][axis] - low) * scale) as usize).min(SAH_BINS - 1)
+        };
+        let mut bin_bounds = [Aabb::EMPTY; SAH_BINS];
+        let mut bin_counts = [0usize; SAH_BINS];
+        for &triangle in order {
+            let bin = bin_of(triangle);
+            bin_bounds[bin].merge(&bounds[triangle as usize]);
+            bin_counts[bin] += 1;
+        }
+
+        // Sweep from the right for the cost of every right side, then from
+        // the left to combine them
+        let mut right_costs = [0.0; SAH_BINS];
+        let mut right = Aabb::EMPTY;
+        let mut right_count = 0;
+        for bin in (1..SAH_BINS).rev() {
+            right.merge(&bin_bounds[bin]);
+            right_count += bin_counts[bin];
+            right_costs[bin] = right_count as f32 * right.half_area();
+        }
+        let mut left = Aabb::EMPTY;
+        let mut left_count = 0;
+        for bin in 1..SAH_BINS {
+            left.merge(&bin_bounds[bin - 1]);
+            left_count += bin_counts[bin - 1];
+            if left_count == 0 || left_count == order.len() {
+                continue;
+            }
+            let cost = left_count as f32 * left.half_area() + right_costs[bin];
+            if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                best = Some((cost, axis, low + bin as f32 / scale));
+            }
+        }
+    }
+
+    let (cost, axis, plane) = best?;
+    // Testing a triangle costs about as much as visiting a node
+    let leaf_cost = order.len() as f32 * node_bounds.half_area();
+    if cost + node_bounds.half_area() >= leaf_cost && order.len() <= MAX_LEAF_TRIANGLES {
+        return None;
+    }
+    Some((axis, plane))
+}
+
+fn ray_hits_box(origin: [f32; 3], direction: [f32; 3], node: &BvhNode, limit: f32) -> bool {
+    let mut near = 0.0f32;
+    let mut far = limit;
+    for axis in 0..3 {
+        let inverse = 1.0 / direction[axis];
+        let t0 = (node.min[axis] - origin[axis]) * inverse;
+        let t1 = (node.max[axis] - origin[axis]) * inverse;
+        near = near.max(t0.min(t1));
+        far = far.min(t0.max(t1));
+    }
+    near <= far
+}
+
+fn ray_triangle(origin: [f32; 3], direction: [f32; 3], [v0, v1, v2]: [[f32; 3]; 3]) -> Option<f32> {
+    use crate::math_utils::{cross, dot};
+    let sub = |a: [f32; 3], b: [f32; 3]| [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    let edge1 = sub(v1, v0);
+    let edge2 = sub(v2, v0);
+    let p = cross(direction, edge2);
+    let determinant = dot(edge1, p);
+    if determinant.abs() < 1e-9 {
+        return None;
+    }
+    let inverse = 1.0 / determinant;
+    let s = sub(origin, v0);
+    let u = dot(s, p) * inverse;
+    let q = cross(s, edge1);
+    let v = dot(direction, q) * inverse;
+    let t = dot(edge2, q) * inverse;
+    (u >= 0.0 && v >= 0.0 && u + v <= 1.0 && t > 0.0).then_some(t)
+}
+
+/// Settings of the path tracer; changing any restarts the accumulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TracerSettings {
+    /// Bounces after the first hit
+    pub max_bounces: u32,
+    /// Paths per pixel every frame
+    pub samples_per_frame: u32,
+    /// Stop tracing once the mean has this many samples
+    pub max_samples: u32,
+    /// Degrees around the vertical axis
+    pub sun_azimuth: f32,
+    /// Degrees above the horizon
+    pub sun_elevation: f32,
+    pub emission_strength: f32,
+    /// Color pixels by the BVH nodes their primary ray visits
+    pub show_bvh: bool,
+}
+
+impl Default for TracerSettings {
+    fn default() -> Self {
+        Self {
+            max_bounces: 4,
+            samples_per_frame: 1,
+            max_samples: 1024,
+            sun_azimuth: 40.0,
+            sun_elevation: 35.0,
+            emission_strength: 6.0,
+            show_bvh: false,
+        }
+    }
+}
+
+impl TracerSettings {
+    /// Unit vector towards the sun
+    pub fn sun_direction(&self) -> [f32; 3] {
+        let (azimuth, elevation) = (
+            self.sun_azimuth.to_radians(),
+            self.sun_elevation.to_radians(),
+        );
+        [
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+            elevation.cos() * azimuth.cos(),
+        ]
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    inverse_view_proj: Mat4,
+    eye: [f32; 3],
+    accumulated: u32,
+    sun_direction: [f32; 3],
+    max_bounces: u32,
+    samples: u32,
+    emission_strength: f32,
+    show_bvh: u32,
+    node_count: u32,
+    size: [u32; 2],
+    _padding: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuVertex {
+    position: [f32; 3],
+    _padding0: f32,
+    normal: [f32; 3],
+    _padding1: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuMaterial {
+    base_color: [f32; 3],
+    metallic: f32,
+    emission: [f32; 3],
+    roughness: f32,
+}
+
+/// Storage buffers of the mesh, in BVH order
+struct SceneBuffers {
+    vertices: wgpu::Buffer,
+    triangles: wgpu::Buffer,
+    nodes: wgpu::Buffer,
+    materials: wgpu::Buffer,
+}
+
+/// The accumulation textures and the bind groups reading one and writing
+/// the other
+struct Accumulation {
+    textures: [wgpu::Texture; 2],
+    trace_bind_groups: [wgpu::BindGroup; 2],
+    display_bind_groups: [wgpu::BindGroup; 2],
+}
+
+/// Path tracer drawing a [`TracerMesh`] with progressive accumulation
+pub struct RayTracer {
+    trace_pipeline: wgpu::ComputePipeline,
+    display_pipeline: wgpu::RenderPipeline,
+    trace_layout: wgpu::BindGroupLayout,
+    display_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    scene: SceneBuffers,
+    accumulation: Accumulation,
+    /// Texture holding the latest mean
+    current: usize,
+    size: (u32, u32),
+    mesh: TracerMesh,
+    bvh: Bvh,
+    view_proj: Mat4,
+    eye: [f32; 3],
+    /// Camera and settings the mean was accumulated with
+    accumulated_for: Option<(Mat4, [f32; 3], TracerSettings)>,
+    accumulated: u32,
+    pub settings: TracerSettings,
+}
+
+impl RayTracer {
+    /// Create the tracer drawing into `color_format` targets of
+    /// `width`x`height`, with no mesh yet
+    pub fn new(
+        device: &wgpu::Device,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let trace_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Ray Tracer Shader"),
+            source: wgpu::ShaderSource::Wgsl(RAY_TRACER_SHADER.into()),
+        });
+        let display_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Ray Tracer Display Shader"),
+            source: wgpu::ShaderSource::Wgsl(RAY_TRACER_DISPLAY_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Ray Tracer Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let storage_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let accumulation_entry = |binding, visibility| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let trace_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Ray Tracer Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_entry(1),
+                storage_entry(2),
+                storage_entry(3),
+                storage_entry(4),
+                accumulation_entry(5, wgpu::ShaderStages::COMPUTE),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: ACCUMULATION_FORMAT,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let display_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Ray Tracer Display Bind Group Layout"),
+            entries: &[accumulation_entry(0, wgpu::ShaderStages::FRAGMENT)],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let trace_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Ray Tracer Pipeline Layout"),
+                bind_group_layouts: &[Some(&trace_layout)],
+                immediate_size: 0,
+            });
+        let display_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Ray Tracer Display Pipeline Layout"),
+                bind_group_layouts: &[Some(&display_layout)],
+                immediate_size: 0,
+            });
+
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let trace_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Ray Tracer Pipeline"),
+            layout: Some(&trace_pipeline_layout),
+            module: &trace_shader,
+            entry_point: Some("trace_paths"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let display_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Ray Tracer Display Pipeline"),
+            layout: Some(&display_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &display_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &display_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        let mesh = TracerMesh::default();
+        let bvh = Bvh::default();
+        let scene = create_scene_buffers(device, &mesh, &bvh);
+        let accumulation = create_accumulation(
+            device,
+            &trace_layout,
+            &display_layout,
+            &uniform_buffer,
+            &scene,
+            (width, height),
+        );
+        Self {
+            trace_pipeline,
+            display_pipeline,
+            trace_layout,
+            display_layout,
+            uniform_buffer,
+            scene,
+            accumulation,
+            current: 0,
+            size: (width, height),
+            mesh,
+            bvh,
+            view_proj: MAT4_IDENTITY,
+            eye: [0.0; 3],
+            accumulated_for: None,
+            accumulated: 0,
+            settings: TracerSettings::default(),
+        }
+    }
+
+    /// Build the BVH of `mesh` and trace it from now on
+    pub fn set_mesh(&mut self, device: &wgpu::Device, mesh: TracerMesh) {
+        self.bvh = Bvh::build(&mesh);
+        self.mesh = mesh;
+        self.scene = create_scene_buffers(device, &self.mesh, &self.bvh);
+        self.accumulation = create_accumulation(
+            device,
+            &self.trace_layout,
+            &self.display_layout,
+            &self.uniform_buffer,
+            &self.scene,
+            self.size,
+        );
+        self.restart();
+    }
+
+    /// Load a model file and trace it; the current mesh stays on failure
+    pub fn load_model(&mut self, device: &wgpu::Device, path: &Path) -> Result<(), RayTracerError> {
+        let mesh = TracerMesh::load(path)?;
+        self.set_mesh(device, mesh);
+        Ok(())
+    }
+
+    pub fn mesh(&self) -> &TracerMesh {
+        &self.mesh
+    }
+
+    pub fn bvh(&self) -> &Bvh {
+        &self.bvh
+    }
+
+    /// Samples per pixel in the image so far
+    pub fn sample_count(&self) -> u32 {
+        self.accumulated
+    }
+
+    /// Whether the image has all of [`TracerSettings::max_samples`]
+    pub fn is_converged(&self) -> bool {
+        self.accumulated >= self.settings.max_samples
+    }
+
+    /// Throw away the samples so far
+    pub fn restart(&mut self) {
+        self.accumulated_for = None;
+        self.accumulated = 0;
+    }
+
+    /// Look through a camera with this view-projection matrix, at `eye`
+    pub fn update(&mut self, view_proj: Mat4, eye: [f32; 3]) {
+        self.view_proj = view_proj;
+        self.eye = eye;
+    }
+
+    /// Trace more samples unless converged, then draw the mean into
+    /// `target`, a view of a texture of the given size
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+    ) {
+        if self.size != (width, height) {
+            self.size = (width, height);
+            self.accumulation = create_accumulation(
+                device,
+                &self.trace_layout,
+                &self.display_layout,
+                &self.uniform_buffer,
+                &self.scene,
+                self.size,
+            );
+            self.restart();
+        }
+        let state = (self.view_proj, self.eye, self.settings);
+        if self.accumulated_for != Some(state) {
+            self.accumulated_for = Some(state);
+            self.accumulated = 0;
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Ray Tracer Encoder"),
+        });
+        let samples = self
+            .settings
+            .samples_per_frame
+            .max(1)
+            .min(self.settings.max_samples.saturating_sub(self.accumulated));
+        if samples > 0 {
+            let uniforms = Uniforms {
+                inverse_view_proj: mat4_inverse(&self.view_proj).unwrap_or(MAT4_IDENTITY),
+                eye: self.eye,
+                accumulated: self.accumulated,
+                sun_direction: self.settings.sun_direction(),
+                max_bounces: self.settings.max_bounces.min(MAX_BOUNCES),
+                samples,
+                emission_strength: self.settings.emission_strength,
+                show_bvh: u32::from(self.settings.show_bvh),
+                node_count: self.bvh.nodes.len() as u32,
+                size: [width, height],
+                _padding: [0; 2],
+            };
+            upload_belt::write_buffer(
+                queue,
+                &self.uniform_buffer,
+                0,
+                bytemuck::bytes_of(&uniforms),
+            );
+
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Ray Tracer Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.trace_pipeline);
+            compute_pass.set_bind_group(0, &self.accumulation.trace_bind_groups[self.current], &[]);
+            tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+            compute_pass.dispatch_workgroups(
+                width.div_ceil(WORKGROUP_SIZE),
+                height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+            self.current = 1 - self.current;
+            self.accumulated += samples;
+        }
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Ray Tracer Display Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.display_pipeline);
+            render_pass.set_bind_group(
+                0,
+                &self.accumulation.display_bind_groups[self.current],
+                &[],
+            );
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..3, 0..1);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+
+    /// The `rgba32float` texture holding the mean of the samples so far
+    pub fn accumulation(&self) -> &wgpu::Texture {
+        &self.accumulation.textures[self.current]
+    }
+}
+
+/// Upload the mesh with its triangles in the order of the BVH leaves
+///
+/// Storage bindings can't be empty, so an empty mesh gets one zeroed
+/// element in every buffer; the shader skips the BVH when it has no nodes.
+fn create_scene_buffers(device: &wgpu::Device, mesh: &TracerMesh, bvh: &Bvh) -> SceneBuffers {
+    let vertices: Vec<GpuVertex> = mesh
+        .positions
+        .iter()
+        .zip(&mesh.normals)
+        .map(|(&position, &normal)| GpuVertex {
+            position,
+            _padding0: 0.0,
+            normal,
+            _padding1: 0.0,
+        })
+        .collect();
+    let triangles: Vec<[u32; 4]> = bvh
+        .order
+        .iter()
+        .map(|&triangle| mesh.triangles[triangle as usize])
+        .collect();
+    let materials: Vec<GpuMaterial> = mesh
+        .materials
+        .iter()
+        .map(|material| GpuMaterial {
+            base_color: material.base_color,
+            metallic: material.metallic,
+            emission: material.emission,
+            roughness: material.roughness,
+        })
+        .collect();
+
+    let create = |label, contents: &[u8], element_size: usize| {
+        use wgpu::util::DeviceExt;
+        let zeroed = vec![0; element_size];
+        ApiCoverageTracker::global().record(ApiCategory::Buffer, "create_buffer_init");
+        device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(label),
+            contents: if contents.is_empty() {
+                &zeroed
+            } else {
+                contents
+            },
+            usage: wgpu::BufferUsages::STORAGE,
+        })
+    };
+    SceneBuffers {
+        vertices: create(
+            "Ray Tracer Vertices",
+            bytemuck::cast_slice(&vertices),
+            std::mem::size_of::<GpuVertex>(),
+        ),
+        triangles: create("Ray Tracer Triangles", bytemuck::cast_slice(&triangles), 16),
+        nodes: create(
+            "Ray Tracer BVH Nodes",
+            bytemuck::cast_slice(&bvh.nodes),
+            std::mem::size_of::<BvhNode>(),
+        ),
+        materials: create(
+            "Ray Tracer Materials",
+            bytemuck::cast_slice(&materials),
+            std::mem::size_of::<GpuMaterial>(),
+        ),
+    }
+}
+
+fn create_accumulation(
+    device: &wgpu::Device,
+    trace_layout: &wgpu::BindGroupLayout,
+    display_layout: &wgpu::BindGroupLayout,
+    uniform_buffer: &wgpu::Buffer,
+    scene: &SceneBuffers,
+    (width, height): (u32, u32),
+) -> Accumulation {
+    let tracker = ApiCoverageTracker::global();
+    let textures = [0, 1].map(|_| {
+        tracker.record(ApiCategory::Texture, "create_texture");
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Ray Tracer Accumulation"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: ACCUMULATION_FORMAT,
+            usage: wgpu::TextureUsages::STORAGE_BINDING
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    });
+    let views = textures
+        .each_ref()
+        .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()));
+
+    tracker.record(ApiCategory::BindGroup, "create_bind_group");
+    // Bind group `i` reads texture `i` and writes the other
+    let trace_bind_groups = [0, 1].map(|i| {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Ray Tracer Bind Group"),
+            layout: trace_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: scene.vertices.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: scene.triangles.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: scene.nodes.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: scene.materials.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::TextureView(&views[i]),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&views[1 - i]),
+                },
+            ],
+        })
+    });
+    let display_bind_groups = [0, 1].map(|i| {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Ray Tracer Display Bind Group"),
+            layout: display_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&views[i]),
+            }],
+        })
+    });
+    Accumulation {
+        textures,
+        trace_bind_groups,
+        display_bind_groups,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_loader::{Material, Mesh, ModelVertex};
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    /// A grid of `n`x`n` quads in the xz plane, two triangles each
+    fn grid(n: u32) -> TracerMesh {
+        let mut mesh = TracerMesh {
+            materials: vec![TracerMaterial::default()],
+            ..Default::default()
+        };
+        for z in 0..=n {
+            for x in 0..=n {
+                mesh.positions
+                    .push([x as f32, (x * z % 3) as f32 * 0.1, z as f32]);
+                mesh.normals.push([0.0, 1.0, 0.0]);
+            }
+        }
+        let index = |x: u32, z: u32| z * (n + 1) + x;
+        for z in 0..n {
+            for x in 0..n {
+                let [a, b, c, d] = [
+                    index(x, z),
+                    index(x + 1, z),
+                    index(x + 1, z + 1),
+                    index(x, z + 1),
+                ];
+                mesh.triangles.push([a, b, c, 0]);
+                mesh.triangles.push([a, c, d, 0]);
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        validate(RAY_TRACER_SHADER);
+        validate(RAY_TRACER_DISPLAY_SHADER);
+        let depth = format!("const MAX_BVH_DEPTH: u32 = {}u;", MAX_BVH_DEPTH);
+        assert!(RAY_TRACER_SHADER.contains(&depth));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<Uniforms>(), 128);
+        assert_eq!(std::mem::size_of::<GpuVertex>(), 32);
+        assert_eq!(std::mem::size_of::<GpuMaterial>(), 32);
+        assert_eq!(std::mem::size_of::<BvhNode>(), 32);
+    }
+
+    #[test]
+    fn test_bvh_covers_every_triangle_once() {
+        let mesh = grid(40);
+        let bvh = Bvh::build(&mesh);
+        let mut order = bvh.order.clone();
+        order.sort_unstable();
+        assert_eq!(order, (0..mesh.triangles.len() as u32).collect::<Vec<_>>());
+
+        let mut leaf_slots = 0;
+        for node in &bvh.nodes {
+            if node.is_leaf() {
+                leaf_slots += node.count as usize;
+                // Every triangle of a leaf lies inside its box
+                let first = node.left_first as usize;
+                for &triangle in &bvh.order[first..first + node.count as usize] {
+                    for corner in mesh.corners(triangle as usize) {
+                        for (axis, value) in corner.into_iter().enumerate() {
+                            assert!(node.min[axis] <= value && value <= node.max[axis]);
+                        }
+                    }
+                }
+            } else {
+                // Children sit inside their parent
+                for child in [node.left_first, node.left_first + 1] {
+                    let child = &bvh.nodes[child as usize];
+                    for axis in 0..3 {
+                        assert!(node.min[axis] <= child.min[axis]);
+                        assert!(child.max[axis] <= node.max[axis]);
+                    }
+                }
+            }
+        }
+        assert_eq!(leaf_slots, mesh.triangles.len());
+        assert!(bvh.depth() < MAX_BVH_DEPTH, "depth {}", bvh.depth());
+        assert!(bvh.leaf_count() > 1);
+        assert!(bvh
+            .nodes
+            .iter()
+            .all(|node| node.count as usize <= MAX_LEAF_TRIANGLES));
+    }
+
+    #[test]
+    fn test_bvh_intersection_matches_brute_force() {
+        let mesh = grid(24);
+        let bvh = Bvh::build(&mesh);
+        for i in 0..200 {
+            let origin = [i as f32 * 0.13 - 2.0, 5.0, (i * 7 % 31) as f32 - 3.0];
+            let direction = crate::math_utils::normalize([0.3, -1.0, (i % 5) as f32 * 0.1 - 0.25]);
+            let brute = (0..mesh.triangles.len())
+                .filter_map(|t| ray_triangle(origin, direction, mesh.corners(t)).map(|d| (d, t)))
+                .min_by(|a, b| a.0.total_cmp(&b.0));
+            let found = bvh.intersect(&mesh, origin, direction);
+            assert_eq!(found.map(|(t, _)| t), brute.map(|(t, _)| t), "ray {}", i);
+        }
+    }
+
+    #[test]
+    fn test_empty_mesh_has_no_nodes() {
+        let bvh = Bvh::build(&TracerMesh::default());
+        assert!(bvh.nodes.is_empty());
+        assert_eq!(bvh.depth(), 0);
+        assert_eq!(
+            bvh.intersect(&TracerMesh::default(), [0.0; 3], [0.0, 0.0, 1.0]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_from_model_merges_meshes_and_materials() {
+        let vertex = |x: f32| ModelVertex::new([x, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0; 2]);
+        let model = ModelData {
+            meshes: vec![
+                Mesh {
+                    vertices: vec![vertex(0.0), vertex(1.0), vertex(2.0)],
+                    indices: vec![0, 1, 2],
+                    material_index: Some(0),
+                },
+                Mesh {
+                    vertices: vec![vertex(3.0), vertex(4.0), vertex(5.0)],
+                    indices: vec![2, 1, 0],
+                    material_index: None,
+                },
+            ],
+            materials: vec![Material {
+                diffuse_color: [0.1, 0.2, 0.3, 1.0],
+                metallic: 1.0,
+                emissive: [0.5; 3],
+                ..Default::default()
+            }],
+            textures: Vec::new(),
+            vertex_count: 6,
+            index_count: 6,
+        };
+        let mesh = TracerMesh::from_model(&model);
+        assert_eq!(mesh.triangles, vec![[0, 1, 2, 0], [5, 4, 3, 1]]);
+        assert_eq!(mesh.materials.len(), 2);
+        assert_eq!(mesh.materials[0].base_color, [0.1, 0.2, 0.3]);
+        assert_eq!(mesh.materials[0].emission, [0.5; 3]);
+        assert_eq!(mesh.materials[1], TracerMaterial::default());
+        assert_eq!(mesh.bounds(), ([0.0; 3], [5.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_sun_direction_is_a_unit_vector() {
+        let settings = TracerSettings {
+            sun_elevation: 90.0,
+            ..Default::default()
+        };
+        let [x, y, z] = settings.sun_direction();
+        assert!(x.abs() < 1e-6 && (y - 1.0).abs() < 1e-6 && z.abs() < 1e-6);
+        let [x, y, z] = TracerSettings::default().sun_direction();
+        assert!(((x * x + y * y + z * z).sqrt() - 1.0).abs() < 1e-6);
+    }
+}
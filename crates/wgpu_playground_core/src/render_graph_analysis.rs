@@ -0,0 +1,506 @@
+//! What wgpu does between the passes of a [`RenderGraph`]
+//!
+//! WebGPU has no barriers or image layouts in its API: wgpu tracks how every
+//! texture is used and inserts the transitions itself when a usage changes,
+//! e.g. from color attachment to sampled texture. [`analyze`] replays a
+//! graph the same way and lists those transitions, named after the internal
+//! usage states of wgpu-hal. It also computes when each target's contents
+//! are alive, so targets whose lifetimes never overlap can be shown sharing
+//! one allocation, the memory aliasing a frame graph with transient
+//! resources would do. wgpu itself gives every texture its own memory.
+
+use crate::render_targets::{GraphPass, PassContent, RenderGraph, RenderTargetDesc};
+
+/// How a pass uses a target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Color attachment, loading the previous contents or clearing them
+    Color { load: bool },
+    /// Resolve target of the multisampled color attachment
+    Resolve,
+    /// Depth attachment, loading the previous contents or clearing them
+    Depth { load: bool },
+    /// Texture the pass samples
+    Sampled,
+}
+
+impl Access {
+    pub fn state(self) -> TextureState {
+        match self {
+            Access::Color { .. } | Access::Resolve => TextureState::ColorTarget,
+            Access::Depth { .. } => TextureState::DepthStencilWrite,
+            Access::Sampled => TextureState::Resource,
+        }
+    }
+
+    /// Whether the pass needs what earlier passes left in the target
+    pub fn reads_contents(self) -> bool {
+        matches!(
+            self,
+            Access::Color { load: true } | Access::Depth { load: true } | Access::Sampled
+        )
+    }
+
+    /// One letter for the lifetime timeline
+    pub fn short(self) -> &'static str {
+        match self {
+            Access::Color { .. } => "C",
+            Access::Resolve => "R",
+            Access::Depth { .. } => "D",
+            Access::Sampled => "S",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Access::Color { load: false } => "color attachment, cleared",
+            Access::Color { load: true } => "color attachment, loaded",
+            Access::Resolve => "resolve target",
+            Access::Depth { load: false } => "depth attachment, cleared",
+            Access::Depth { load: true } => "depth attachment, loaded",
+            Access::Sampled => "sampled",
+        }
+    }
+}
+
+/// Usage state wgpu tracks for a texture, named after wgpu-hal's
+/// `TextureUses`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureState {
+    /// Whatever the previous submission left; contents not yet needed
+    Undefined,
+    ColorTarget,
+    DepthStencilWrite,
+    /// Bound as a sampled texture
+    Resource,
+    /// Source of a texture-to-buffer copy
+    CopySrc,
+}
+
+impl TextureState {
+    pub fn name(self) -> &'static str {
+        match self {
+            TextureState::Undefined => "UNDEFINED",
+            TextureState::ColorTarget => "COLOR_TARGET",
+            TextureState::DepthStencilWrite => "DEPTH_STENCIL_WRITE",
+            TextureState::Resource => "RESOURCE",
+            TextureState::CopySrc => "COPY_SRC",
+        }
+    }
+
+    /// The matching Vulkan image layout
+    pub fn vulkan_layout(self) -> &'static str {
+        match self {
+            TextureState::Undefined => "UNDEFINED",
+            TextureState::ColorTarget => "COLOR_ATTACHMENT_OPTIMAL",
+            TextureState::DepthStencilWrite => "DEPTH_STENCIL_ATTACHMENT_OPTIMAL",
+            TextureState::Resource => "SHADER_READ_ONLY_OPTIMAL",
+            TextureState::CopySrc => "TRANSFER_SRC_OPTIMAL",
+        }
+    }
+}
+
+/// A usage change wgpu records a barrier for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    /// Pass the transition happens before; `None` for the readback after
+    /// the graph
+    pub pass: Option<usize>,
+    pub target: String,
+    pub from: TextureState,
+    pub to: TextureState,
+    /// The contents are discarded, so the barrier doesn't preserve them
+    pub discards: bool,
+}
+
+impl Transition {
+    /// One sentence on what the barrier waits for and why
+    pub fn explanation(&self) -> String {
+        let wait = match self.from {
+            TextureState::Undefined => "nothing to wait for".to_string(),
+            TextureState::ColorTarget | TextureState::DepthStencilWrite => {
+                "waits for the earlier pass to finish writing it".to_string()
+            }
+            TextureState::Resource => {
+                "waits for the earlier pass to finish sampling it".to_string()
+            }
+            TextureState::CopySrc => "waits for the copy to finish reading it".to_string(),
+        };
+        let purpose = match self.to {
+            TextureState::ColorTarget | TextureState::DepthStencilWrite if self.discards => {
+                "the pass clears it, so the old contents need not be kept"
+            }
+            TextureState::ColorTarget | TextureState::DepthStencilWrite => {
+                "the pass draws over what is there"
+            }
+            TextureState::Resource => "so the fragment shader sees every write",
+            TextureState::CopySrc => "so the inspector copies finished texels",
+            TextureState::Undefined => "",
+        };
+        format!(
+            "{}: {} → {} (Vulkan layout {} → {}); {}, {}",
+            self.target,
+            self.from.name(),
+            self.to.name(),
+            self.from.vulkan_layout(),
+            self.to.vulkan_layout(),
+            wait,
+            purpose
+        )
+    }
+}
+
+/// When a target's contents are in use
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetLifetime {
+    pub target: String,
+    /// First and last pass using the target
+    pub first: usize,
+    pub last: usize,
+    /// Use in every pass, `None` where the pass doesn't touch the target
+    pub accesses: Vec<Option<Access>>,
+    /// Bytes the texture occupies
+    pub bytes: u64,
+    /// Contents are neither needed before the first pass nor after the last,
+    /// so the memory only has to exist in between
+    pub transient: bool,
+}
+
+impl TargetLifetime {
+    /// A transient target used by a single pass never needs its contents
+    /// stored; `StoreOp::Discard` would let tile-based GPUs keep it in tile
+    /// memory
+    pub fn could_discard(&self) -> bool {
+        self.transient && self.first == self.last
+    }
+
+    fn overlaps(&self, other: &TargetLifetime) -> bool {
+        self.first <= other.last && other.first <= self.last
+    }
+}
+
+/// Transient targets that could share one allocation, one after another
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasGroup {
+    /// In order of first use
+    pub targets: Vec<String>,
+    /// Size of the shared allocation, the largest member's
+    pub bytes: u64,
+}
+
+/// Lifetimes, transitions and aliasing of one graph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphAnalysis {
+    /// Targets used by the graph, in order of first use
+    pub lifetimes: Vec<TargetLifetime>,
+    pub transitions: Vec<Transition>,
+    /// Groups of more than one target
+    pub alias_groups: Vec<AliasGroup>,
+}
+
+impl GraphAnalysis {
+    /// Transitions before `pass`, or after the graph for `None`
+    pub fn transitions_before(&self, pass: Option<usize>) -> impl Iterator<Item = &Transition> {
+        self.transitions.iter().filter(move |t| t.pass == pass)
+    }
+
+    pub fn lifetime(&self, target: &str) -> Option<&TargetLifetime> {
+        self.lifetimes.iter().find(|l| l.target == target)
+    }
+
+    /// Memory of every target the graph uses, each in its own allocation
+    pub fn total_bytes(&self) -> u64 {
+        self.lifetimes.iter().map(|l| l.bytes).sum()
+    }
+
+    /// Memory with the alias groups sharing their allocations
+    pub fn aliased_bytes(&self) -> u64 {
+        let saved: u64 = self
+            .alias_groups
+            .iter()
+            .map(|group| {
+                let members: u64 = group
+                    .targets
+                    .iter()
+                    .filter_map(|name| self.lifetime(name))
+                    .map(|l| l.bytes)
+                    .sum();
+                members - group.bytes
+            })
+            .sum();
+        self.total_bytes() - saved
+    }
+}
+
+/// Bytes of a target's texture
+pub fn target_bytes(desc: &RenderTargetDesc) -> u64 {
+    let texel = desc.format.block_copy_size(None).unwrap_or(4) as u64;
+    desc.width as u64 * desc.height as u64 * desc.sample_count as u64 * texel
+}
+
+/// Targets `pass` uses and how
+pub fn pass_accesses(pass: &GraphPass) -> Vec<(&str, Access)> {
+    let mut accesses = Vec::new();
+    if let PassContent::Sample(source) = &pass.content {
+        accesses.push((source.as_str(), Access::Sampled));
+    }
+    if let Some(color) = &pass.color {
+        accesses.push((
+            color.as_str(),
+            Access::Color {
+                load: pass.keep_contents,
+            },
+        ));
+    }
+    if let Some(resolve) = &pass.resolve {
+        accesses.push((resolve.as_str(), Access::Resolve));
+    }
+    if let Some(depth) = &pass.depth {
+        accesses.push((
+            depth.as_str(),
+            Access::Depth {
+                load: pass.keep_contents,
+            },
+        ));
+    }
+    accesses
+}
+
+/// Replay `graph` over `targets`
+///
+/// `exported` names the targets read after the graph, such as the one in
+/// the inspector; their contents must outlive it, so they are never
+/// transient. Targets the graph names that aren't in `targets` are skipped.
+pub fn analyze(
+    graph: &RenderGraph,
+    targets: &[RenderTargetDesc],
+    exported: &[&str],
+) -> GraphAnalysis {
+    let pass_count = graph.passes.len();
+    let mut lifetimes: Vec<TargetLifetime> = Vec::new();
+    let mut states: Vec<TextureState> = Vec::new();
+    let mut transitions = Vec::new();
+
+    for (index, pass) in graph.passes.iter().enumerate() {
+        for (name, access) in pass_accesses(pass) {
+            let Some(desc) = targets.iter().find(|desc| desc.name == name) else {
+                continue;
+            };
+            let slot = match lifetimes.iter().position(|l| l.target == name) {
+                Some(slot) => slot,
+                None => {
+                    lifetimes.push(TargetLifetime {
+                        target: name.to_string(),
+                        first: index,
+                        last: index,
+                        accesses: vec![None; pass_count],
+                        bytes: target_bytes(desc),
+                        // Reading contents before anything wrote them means
+                        // they come from outside the graph
+                        transient: !access.reads_contents() && !exported.contains(&name),
+                    });
+                    states.push(TextureState::Undefined);
+                    lifetimes.len() - 1
+                }
+            };
+            let lifetime = &mut lifetimes[slot];
+            lifetime.last = index;
+            lifetime.accesses[index] = Some(access);
+
+            let state = access.state();
+            if states[slot] != state {
+                transitions.push(Transition {
+                    pass: Some(index),
+                    target: name.to_string(),
+                    from: states[slot],
+                    to: state,
+                    discards: !access.reads_contents(),
+                });
+                states[slot] = state;
+            }
+        }
+    }
+
+    for name in exported {
+        if let Some(slot) = lifetimes.iter().position(|l| l.target == *name) {
+            transitions.push(Transition {
+                pass: None,
+                target: name.to_string(),
+                from: states[slot],
+                to: TextureState::CopySrc,
+                discards: false,
+            });
+        }
+    }
+
+    let alias_groups = alias_groups(&lifetimes);
+    GraphAnalysis {
+        lifetimes,
+        transitions,
+        alias_groups,
+    }
+}
+
+/// Assign transient targets to shared allocations, each going to the
+/// allocation closest in size whose previous users are all finished
+fn alias_groups(lifetimes: &[TargetLifetime]) -> Vec<AliasGroup> {
+    let mut transient: Vec<&TargetLifetime> = lifetimes.iter().filter(|l| l.transient).collect();
+    transient.sort_by_key(|l| (l.first, l.last));
+
+    let mut groups: Vec<(AliasGroup, Vec<&TargetLifetime>)> = Vec::new();
+    for lifetime in transient {
+        let free = groups
+            .iter_mut()
+            .filter(|(_, members)| members.iter().all(|member| !member.overlaps(lifetime)))
+            .min_by_key(|(group, _)| group.bytes.abs_diff(lifetime.bytes));
+        match free {
+            Some((group, members)) => {
+                group.targets.push(lifetime.target.clone());
+                group.bytes = group.bytes.max(lifetime.bytes);
+                members.push(lifetime);
+            }
+            None => groups.push((
+                AliasGroup {
+                    targets: vec![lifetime.target.clone()],
+                    bytes: lifetime.bytes,
+                },
+                vec![lifetime],
+            )),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(group, _)| group)
+        .filter(|group| group.targets.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(name: &str) -> RenderTargetDesc {
+        RenderTargetDesc::new(name, wgpu::TextureFormat::Rgba8Unorm, 64, 64)
+    }
+
+    fn draw(name: &str, color: &str) -> GraphPass {
+        GraphPass {
+            color: Some(color.to_string()),
+            ..GraphPass::new(name)
+        }
+    }
+
+    fn sample(name: &str, source: &str, color: &str) -> GraphPass {
+        GraphPass {
+            content: PassContent::Sample(source.to_string()),
+            ..draw(name, color)
+        }
+    }
+
+    #[test]
+    fn test_example_graph() {
+        let (targets, graph) = RenderGraph::example();
+        let analysis = analyze(&graph, &targets, &["post"]);
+
+        let names: Vec<&str> = analysis
+            .lifetimes
+            .iter()
+            .map(|l| l.target.as_str())
+            .collect();
+        assert_eq!(names, ["scene_msaa", "scene", "scene_depth", "post"]);
+        let scene = analysis.lifetime("scene").unwrap();
+        assert_eq!((scene.first, scene.last), (0, 1));
+        assert_eq!(
+            scene.accesses,
+            [Some(Access::Resolve), Some(Access::Sampled)]
+        );
+        assert!(scene.transient);
+        assert!(!analysis.lifetime("post").unwrap().transient);
+        // The multisampled attachments only live inside the scene pass
+        assert!(analysis.lifetime("scene_msaa").unwrap().could_discard());
+        assert!(analysis.lifetime("scene_depth").unwrap().could_discard());
+        assert!(!scene.could_discard());
+
+        let before_post: Vec<_> = analysis.transitions_before(Some(1)).collect();
+        assert_eq!(before_post.len(), 2);
+        assert_eq!(
+            (
+                &*before_post[0].target,
+                before_post[0].from,
+                before_post[0].to
+            ),
+            ("scene", TextureState::ColorTarget, TextureState::Resource)
+        );
+        assert_eq!(before_post[1].target, "post");
+        let readback: Vec<_> = analysis.transitions_before(None).collect();
+        assert_eq!(readback.len(), 1);
+        assert_eq!(readback[0].to, TextureState::CopySrc);
+        assert!(readback[0].explanation().contains("TRANSFER_SRC_OPTIMAL"));
+
+        // The MSAA color and depth attachments end with the scene pass, so
+        // "post" can't reuse them, but nothing else is left to alias
+        assert!(analysis.alias_groups.is_empty());
+        assert_eq!(analysis.aliased_bytes(), analysis.total_bytes());
+    }
+
+    #[test]
+    fn test_chain_aliases_alternate_targets() {
+        let targets = [color("a"), color("b"), color("c"), color("d")];
+        let graph = RenderGraph {
+            passes: vec![
+                draw("A", "a"),
+                sample("B", "a", "b"),
+                sample("C", "b", "c"),
+                sample("D", "c", "d"),
+            ],
+        };
+        let analysis = analyze(&graph, &targets, &["d"]);
+
+        assert_eq!(
+            analysis.alias_groups,
+            [AliasGroup {
+                targets: vec!["a".to_string(), "c".to_string()],
+                bytes: 64 * 64 * 4,
+            }]
+        );
+        assert_eq!(analysis.total_bytes(), 4 * 64 * 64 * 4);
+        assert_eq!(analysis.aliased_bytes(), 3 * 64 * 64 * 4);
+    }
+
+    #[test]
+    fn test_loaded_targets_are_not_transient() {
+        let targets = [color("a"), color("b")];
+        let mut accumulate = draw("Accumulate", "a");
+        accumulate.keep_contents = true;
+        let graph = RenderGraph {
+            passes: vec![accumulate, draw("Other", "b")],
+        };
+        let analysis = analyze(&graph, &targets, &[]);
+
+        assert!(!analysis.lifetime("a").unwrap().transient);
+        assert!(analysis.lifetime("b").unwrap().transient);
+        assert!(analysis.alias_groups.is_empty());
+        assert!(!analysis.transitions[0].discards);
+        assert!(analysis.transitions[1].discards);
+    }
+
+    #[test]
+    fn test_same_usage_needs_no_transition() {
+        let targets = [color("a")];
+        let graph = RenderGraph {
+            passes: vec![draw("First", "a"), draw("Second", "a")],
+        };
+        let analysis = analyze(&graph, &targets, &[]);
+        assert_eq!(analysis.transitions.len(), 1);
+        assert_eq!(analysis.transitions[0].pass, Some(0));
+        assert_eq!(analysis.transitions[0].from, TextureState::Undefined);
+    }
+
+    #[test]
+    fn test_target_bytes() {
+        let msaa = color("a").with_sample_count(4);
+        assert_eq!(target_bytes(&msaa), 64 * 64 * 4 * 4);
+        let depth = RenderTargetDesc::new("d", wgpu::TextureFormat::Depth16Unorm, 10, 10);
+        assert_eq!(target_bytes(&depth), 200);
+    }
+}
@@ -250,13 +250,13 @@ impl RenderPassPanel {
                     match op {
                         LoadOpChoice::Clear => {
                             ui.selectable_value(load_op, op, op.name()).webgpu_tooltip(
-                                crate::tooltip::load_store_op::LOAD_OP_CLEAR.description,
+                                &crate::tooltip::load_store_op::LOAD_OP_CLEAR.description(),
                                 crate::tooltip::load_store_op::LOAD_OP_CLEAR.spec_anchor,
                             );
                         }
                         LoadOpChoice::Load => {
                             ui.selectable_value(load_op, op, op.name()).webgpu_tooltip(
-                                crate::tooltip::load_store_op::LOAD_OP_LOAD.description,
+                                &crate::tooltip::load_store_op::LOAD_OP_LOAD.description(),
                                 crate::tooltip::load_store_op::LOAD_OP_LOAD.spec_anchor,
                             );
                         }
@@ -274,13 +274,13 @@ impl RenderPassPanel {
                     match op {
                         StoreOpChoice::Store => {
                             ui.selectable_value(store_op, op, op.name()).webgpu_tooltip(
-                                crate::tooltip::load_store_op::STORE_OP_STORE.description,
+                                &crate::tooltip::load_store_op::STORE_OP_STORE.description(),
                                 crate::tooltip::load_store_op::STORE_OP_STORE.spec_anchor,
                             );
                         }
                         StoreOpChoice::Discard => {
                             ui.selectable_value(store_op, op, op.name()).webgpu_tooltip(
-                                crate::tooltip::load_store_op::STORE_OP_DISCARD.description,
+                                &crate::tooltip::load_store_op::STORE_OP_DISCARD.description(),
                                 crate::tooltip::load_store_op::STORE_OP_DISCARD.spec_anchor,
                             );
                         }
@@ -320,7 +320,7 @@ impl RenderPassPanel {
 
                 ui.checkbox(&mut self.enable_color_attachment, "Enable color attachment")
                     .webgpu_tooltip(
-                        crate::tooltip::load_store_op::COLOR_ATTACHMENT.description,
+                        &crate::tooltip::load_store_op::COLOR_ATTACHMENT.description(),
                         crate::tooltip::load_store_op::COLOR_ATTACHMENT.spec_anchor,
                     );
 
@@ -393,7 +393,7 @@ impl RenderPassPanel {
 
                 ui.checkbox(&mut self.enable_depth_stencil, "Enable depth-stencil attachment")
                     .webgpu_tooltip(
-                        crate::tooltip::load_store_op::DEPTH_STENCIL_ATTACHMENT.description,
+                        &crate::tooltip::load_store_op::DEPTH_STENCIL_ATTACHMENT.description(),
                         crate::tooltip::load_store_op::DEPTH_STENCIL_ATTACHMENT.spec_anchor,
                     );
 
@@ -1,14 +1,16 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::{Arc, Mutex};
 use wgpu::{Device, RenderPipeline};
 
-/// Type alias for the pipeline cache map
-///
-/// Maps pipeline names (String) to compiled render pipeline instances wrapped in Arc.
-/// Uses Arc<Mutex<...>> for thread-safe access and sharing of the cache across multiple
-/// parts of the application. The inner Arc allows multiple references to the same pipeline.
-type PipelineCacheMap = Arc<Mutex<HashMap<String, Arc<RenderPipeline>>>>;
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::device_recovery::ResourceRecreate;
+use crate::shader::{ShaderError, ShaderModule};
+
+/// Number of pipelines a [`PipelineCache`] keeps unless configured otherwise
+pub const DEFAULT_PIPELINE_CACHE_CAPACITY: usize = 64;
 
 /// Errors that can occur during render pipeline operations
 #[derive(Debug)]
@@ -413,6 +415,56 @@ impl FrontFace {
     }
 }
 
+/// Polygon rasterization mode
+///
+/// Controls how triangles are rasterized: filled, as their edges only, or
+/// as their vertices only. Only applies to triangle topologies. `Line` and
+/// `Point` are native extensions that need a device feature.
+///
+/// # Examples
+///
+/// True wireframe of a triangle mesh:
+/// ```
+/// use wgpu_playground_core::render_pipeline::PolygonMode;
+///
+/// let mode = PolygonMode::Line;
+/// assert_eq!(mode.required_features(), wgpu::Features::POLYGON_MODE_LINE);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonMode {
+    /// Fill the triangles
+    Fill,
+    /// Draw the triangle edges as lines
+    Line,
+    /// Draw the triangle vertices as points
+    Point,
+}
+
+impl PolygonMode {
+    /// Convert to wgpu::PolygonMode
+    pub fn to_wgpu(&self) -> wgpu::PolygonMode {
+        match self {
+            PolygonMode::Fill => wgpu::PolygonMode::Fill,
+            PolygonMode::Line => wgpu::PolygonMode::Line,
+            PolygonMode::Point => wgpu::PolygonMode::Point,
+        }
+    }
+
+    /// Device features a pipeline using this mode needs
+    pub fn required_features(&self) -> wgpu::Features {
+        match self {
+            PolygonMode::Fill => wgpu::Features::empty(),
+            PolygonMode::Line => wgpu::Features::POLYGON_MODE_LINE,
+            PolygonMode::Point => wgpu::Features::POLYGON_MODE_POINT,
+        }
+    }
+
+    /// Whether a device with `features` can use this mode
+    pub fn is_supported(&self, features: wgpu::Features) -> bool {
+        features.contains(self.required_features())
+    }
+}
+
 /// Primitive state configuration
 #[derive(Debug, Clone, Copy)]
 pub struct PrimitiveState {
@@ -422,6 +474,8 @@ pub struct PrimitiveState {
     pub cull_mode: CullMode,
     /// Front face winding order
     pub front_face: FrontFace,
+    /// Polygon rasterization mode
+    pub polygon_mode: PolygonMode,
 }
 
 impl PrimitiveState {
@@ -438,6 +492,7 @@ impl PrimitiveState {
             topology: PrimitiveTopology::TriangleList,
             cull_mode: CullMode::None,
             front_face: FrontFace::Ccw,
+            polygon_mode: PolygonMode::Fill,
         }
     }
 
@@ -459,6 +514,12 @@ impl PrimitiveState {
         self
     }
 
+    /// Set the polygon rasterization mode
+    pub fn with_polygon_mode(mut self, polygon_mode: PolygonMode) -> Self {
+        self.polygon_mode = polygon_mode;
+        self
+    }
+
     /// Convert to wgpu::PrimitiveState
     pub fn to_wgpu(&self) -> wgpu::PrimitiveState {
         wgpu::PrimitiveState {
@@ -467,7 +528,7 @@ impl PrimitiveState {
             front_face: self.front_face.to_wgpu(),
             cull_mode: self.cull_mode.to_wgpu(),
             unclipped_depth: false,
-            polygon_mode: wgpu::PolygonMode::Fill,
+            polygon_mode: self.polygon_mode.to_wgpu(),
             conservative: false,
         }
     }
@@ -633,7 +694,7 @@ impl StencilOperation {
 }
 
 /// Stencil face state
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct StencilFaceState {
     /// Comparison function
     pub compare: CompareFunction,
@@ -1514,6 +1575,38 @@ impl RenderPipelineDescriptor {
         &self.fragment_targets
     }
 
+    /// Get the vertex shader entry point
+    pub fn vertex_entry_point(&self) -> &str {
+        &self.vertex_entry_point
+    }
+
+    /// Get the fragment shader entry point
+    pub fn fragment_entry_point(&self) -> &str {
+        &self.fragment_entry_point
+    }
+
+    /// Feed every field that affects the compiled pipeline to `state`
+    ///
+    /// The label only names the pipeline in debuggers, so it is left out.
+    fn hash_state<H: Hasher>(&self, state: &mut H) {
+        for layout in &self.vertex_buffers {
+            layout.array_stride.hash(state);
+            layout.step_mode.to_wgpu().hash(state);
+            layout.to_wgpu_attributes().hash(state);
+        }
+        self.primitive.to_wgpu().hash(state);
+        self.depth_stencil
+            .as_ref()
+            .map(|ds| ds.to_wgpu())
+            .hash(state);
+        self.multisample.to_wgpu().hash(state);
+        for target in &self.fragment_targets {
+            target.to_wgpu().hash(state);
+        }
+        self.vertex_entry_point.hash(state);
+        self.fragment_entry_point.hash(state);
+    }
+
     /// Validate the pipeline descriptor
     pub fn validate(&self) -> Result<(), RenderPipelineError> {
         // Validate vertex buffer layouts
@@ -1581,6 +1674,7 @@ impl RenderPipelineDescriptor {
         vertex_shader: &crate::shader::ShaderModule,
         fragment_shader: Option<&crate::shader::ShaderModule>,
     ) -> Result<RenderPipeline, RenderPipelineError> {
+        crate::cpu_scope!("Create render pipeline");
         log::debug!(
             "Creating render pipeline: label={:?}, vertex_entry={}, fragment_entry={}",
             self.label,
@@ -1650,7 +1744,7 @@ impl RenderPipelineDescriptor {
                 targets: &fragment_targets,
             }),
             multiview_mask: None,
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         log::info!(
@@ -1661,13 +1755,208 @@ impl RenderPipelineDescriptor {
     }
 }
 
-/// Pipeline cache for storing compiled pipelines
+/// Identifies a compiled render pipeline by everything that went into it
+///
+/// Two descriptors that differ only in their label share a key, and so do
+/// shaders with the same source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PipelineKey {
+    /// Hash of the descriptor state, plus the layout once one is added
+    state: u64,
+    /// [`ShaderModule::source_hash`] of the vertex shader
+    vertex_shader: u64,
+    /// [`ShaderModule::source_hash`] of the fragment shader, if any
+    fragment_shader: Option<u64>,
+}
+
+impl PipelineKey {
+    /// Derive the key of the pipeline `descriptor` would create from these
+    /// shaders
+    ///
+    /// # Examples
+    /// ```
+    /// use wgpu_playground_core::render_pipeline::{PipelineKey, RenderPipelineDescriptor};
+    /// use wgpu_playground_core::shader::ShaderModule;
+    ///
+    /// let shader = ShaderModule::from_source("@vertex fn main() {}", None).unwrap();
+    /// let a = PipelineKey::new(&RenderPipelineDescriptor::new(Some("a")), &shader, None);
+    /// let b = PipelineKey::new(&RenderPipelineDescriptor::new(Some("b")), &shader, None);
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn new(
+        descriptor: &RenderPipelineDescriptor,
+        vertex_shader: &ShaderModule,
+        fragment_shader: Option<&ShaderModule>,
+    ) -> Self {
+        let mut hasher = DefaultHasher::new();
+        descriptor.hash_state(&mut hasher);
+        Self {
+            state: hasher.finish(),
+            vertex_shader: vertex_shader.source_hash(),
+            fragment_shader: fragment_shader.map(ShaderModule::source_hash),
+        }
+    }
+
+    /// The key of the same pipeline created with `layout`
+    ///
+    /// Pipeline layouts hash by identity, so pipelines only share an entry
+    /// when they use the same layout object.
+    pub fn with_layout(self, layout: &wgpu::PipelineLayout) -> Self {
+        let mut hasher = DefaultHasher::new();
+        self.state.hash(&mut hasher);
+        layout.hash(&mut hasher);
+        Self {
+            state: hasher.finish(),
+            ..self
+        }
+    }
+
+    /// Whether the pipeline was built from the shader with this source hash
+    pub fn uses_shader(&self, source_hash: u64) -> bool {
+        self.vertex_shader == source_hash || self.fragment_shader == Some(source_hash)
+    }
+}
+
+impl fmt::Display for PipelineKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.state)
+    }
+}
+
+/// Counters of a [`PipelineCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineCacheStats {
+    /// Pipelines currently cached
+    pub entries: usize,
+    /// Pipelines kept before the least recently used is evicted
+    pub capacity: usize,
+    /// Lookups that found a pipeline
+    pub hits: u64,
+    /// Lookups that did not
+    pub misses: u64,
+    /// Pipelines dropped to stay within the capacity
+    pub evictions: u64,
+    /// Pipelines dropped because a shader they use changed
+    pub invalidations: u64,
+}
+
+impl PipelineCacheStats {
+    /// Hits and misses together
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Fraction of lookups that hit, or `None` before the first lookup
+    pub fn hit_rate(&self) -> Option<f64> {
+        match self.lookups() {
+            0 => None,
+            lookups => Some(self.hits as f64 / lookups as f64),
+        }
+    }
+}
+
+/// A cached value and when it was last used
+struct CacheEntry<T> {
+    value: T,
+    last_used: u64,
+}
+
+/// Least recently used map behind [`PipelineCache`], generic so that the
+/// bookkeeping can be tested without a device
+struct LruCache<T> {
+    entries: HashMap<PipelineKey, CacheEntry<T>>,
+    /// Incremented on every access to order entries by recency
+    clock: u64,
+    stats: PipelineCacheStats,
+}
+
+impl<T: Clone> LruCache<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            clock: 0,
+            stats: PipelineCacheStats {
+                capacity: capacity.max(1),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn get(&mut self, key: &PipelineKey) -> Option<T> {
+        let now = self.tick();
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = now;
+                self.stats.hits += 1;
+                Some(entry.value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: PipelineKey, value: T) {
+        let last_used = self.tick();
+        self.entries.insert(key, CacheEntry { value, last_used });
+        self.evict_to(self.stats.capacity);
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.stats.capacity = capacity.max(1);
+        self.evict_to(self.stats.capacity);
+    }
+
+    /// Drop the least recently used entries until at most `capacity` remain
+    fn evict_to(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key)
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+
+    /// Drop the entries built from the shader with this source hash
+    fn invalidate_shader(&mut self, source_hash: u64) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|key, _| !key.uses_shader(source_hash));
+        let removed = before - self.entries.len();
+        self.stats.invalidations += removed as u64;
+        removed
+    }
+
+    fn stats(&self) -> PipelineCacheStats {
+        PipelineCacheStats {
+            entries: self.entries.len(),
+            ..self.stats
+        }
+    }
+}
+
+/// Cache of compiled render pipelines, keyed by [`PipelineKey`]
+///
+/// Holds at most [`capacity`](Self::capacity) pipelines and evicts the
+/// least recently used one beyond that. Clones share the same cache.
+#[derive(Clone)]
 pub struct PipelineCache {
-    cache: PipelineCacheMap,
+    cache: Arc<Mutex<LruCache<Arc<RenderPipeline>>>>,
 }
 
 impl PipelineCache {
-    /// Create a new pipeline cache
+    /// Create a new pipeline cache with the default capacity
     ///
     /// # Examples
     /// ```
@@ -1676,58 +1965,149 @@ impl PipelineCache {
     /// let cache = PipelineCache::new();
     /// ```
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_PIPELINE_CACHE_CAPACITY)
+    }
+
+    /// Create a pipeline cache holding at most `capacity` pipelines
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            cache: Arc::new(Mutex::new(HashMap::new())),
+            cache: Arc::new(Mutex::new(LruCache::new(capacity))),
         }
     }
 
-    /// Get a pipeline from the cache
+    /// Get a pipeline from the cache, counting a hit or a miss
     ///
     /// # Arguments
     /// * `key` - The cache key for the pipeline
     ///
     /// # Returns
     /// An Option containing an Arc to the pipeline if found
-    pub fn get(&self, key: &str) -> Option<Arc<RenderPipeline>> {
-        let cache = self.cache.lock().unwrap();
-        cache.get(key).cloned()
+    pub fn get(&self, key: &PipelineKey) -> Option<Arc<RenderPipeline>> {
+        let mut cache = self.cache.lock().unwrap();
+        cache.get(key)
     }
 
-    /// Insert a pipeline into the cache
+    /// Insert a pipeline into the cache, evicting the least recently used
+    /// one if the cache is full
     ///
     /// # Arguments
     /// * `key` - The cache key for the pipeline
     /// * `pipeline` - The pipeline to cache
-    pub fn insert(&self, key: String, pipeline: RenderPipeline) {
+    pub fn insert(&self, key: PipelineKey, pipeline: RenderPipeline) -> Arc<RenderPipeline> {
+        let pipeline = Arc::new(pipeline);
         let mut cache = self.cache.lock().unwrap();
-        cache.insert(key, Arc::new(pipeline));
+        cache.insert(key, pipeline.clone());
+        pipeline
+    }
+
+    /// Return the cached pipeline for these inputs, creating and caching it
+    /// on a miss
+    ///
+    /// # Arguments
+    /// * `device` - The wgpu device
+    /// * `layout` - The pipeline layout
+    /// * `descriptor` - The pipeline descriptor
+    /// * `vertex_shader` - The vertex shader module
+    /// * `fragment_shader` - Optional fragment shader module
+    pub fn get_or_create(
+        &self,
+        device: &Device,
+        layout: &wgpu::PipelineLayout,
+        descriptor: &RenderPipelineDescriptor,
+        vertex_shader: &ShaderModule,
+        fragment_shader: Option<&ShaderModule>,
+    ) -> Result<Arc<RenderPipeline>, RenderPipelineError> {
+        let key = PipelineKey::new(descriptor, vertex_shader, fragment_shader).with_layout(layout);
+        if let Some(pipeline) = self.get(&key) {
+            return Ok(pipeline);
+        }
+        log::debug!("Pipeline cache miss: key={}", key);
+        ApiCoverageTracker::global().record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline =
+            descriptor.create_pipeline(device, layout, vertex_shader, fragment_shader)?;
+        Ok(self.insert(key, pipeline))
     }
 
-    /// Check if a pipeline exists in the cache
+    /// Check if a pipeline exists in the cache, without counting a lookup
     ///
     /// # Arguments
     /// * `key` - The cache key to check
-    pub fn contains(&self, key: &str) -> bool {
+    pub fn contains(&self, key: &PipelineKey) -> bool {
         let cache = self.cache.lock().unwrap();
-        cache.contains_key(key)
+        cache.entries.contains_key(key)
     }
 
-    /// Clear all pipelines from the cache
+    /// Drop every pipeline built from the shader with this source hash
+    ///
+    /// Returns the number of pipelines dropped.
+    pub fn invalidate_shader(&self, source_hash: u64) -> usize {
+        let mut cache = self.cache.lock().unwrap();
+        cache.invalidate_shader(source_hash)
+    }
+
+    /// Reload `shader` and, if its source changed, drop the pipelines built
+    /// from the old source
+    ///
+    /// # Returns
+    /// Ok(true) if the shader changed, as [`ShaderModule::reload`]
+    pub fn reload_shader(&self, shader: &mut ShaderModule) -> Result<bool, ShaderError> {
+        let old_hash = shader.source_hash();
+        let changed = shader.reload()?;
+        if changed {
+            let dropped = self.invalidate_shader(old_hash);
+            log::debug!(
+                "Shader {:?} changed, dropped {} cached pipelines",
+                shader.label(),
+                dropped
+            );
+        }
+        Ok(changed)
+    }
+
+    /// Clear all pipelines from the cache; the counters are kept
     pub fn clear(&self) {
         let mut cache = self.cache.lock().unwrap();
-        cache.clear();
+        cache.entries.clear();
     }
 
     /// Get the number of cached pipelines
     pub fn len(&self) -> usize {
         let cache = self.cache.lock().unwrap();
-        cache.len()
+        cache.entries.len()
     }
 
     /// Check if the cache is empty
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Maximum number of cached pipelines
+    pub fn capacity(&self) -> usize {
+        let cache = self.cache.lock().unwrap();
+        cache.stats.capacity
+    }
+
+    /// Change the capacity, evicting the least recently used pipelines if
+    /// more are cached; a capacity of 0 is raised to 1
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.set_capacity(capacity);
+    }
+
+    /// Current size and counters
+    pub fn stats(&self) -> PipelineCacheStats {
+        let cache = self.cache.lock().unwrap();
+        cache.stats()
+    }
+
+    /// Zero the hit, miss, eviction and invalidation counters
+    pub fn reset_stats(&self) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.stats = PipelineCacheStats {
+            capacity: cache.stats.capacity,
+            ..Default::default()
+        };
+    }
 }
 
 impl Default for PipelineCache {
@@ -1736,6 +2116,13 @@ impl Default for PipelineCache {
     }
 }
 
+impl ResourceRecreate for PipelineCache {
+    fn recreate_resources(&mut self, _device: &Device, _queue: &wgpu::Queue) {
+        // Cached pipelines belong to the lost device; callers rebuild on miss
+        self.clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1783,6 +2170,7 @@ mod tests {
         assert_eq!(state.topology, PrimitiveTopology::TriangleList);
         assert_eq!(state.cull_mode, CullMode::None);
         assert_eq!(state.front_face, FrontFace::Ccw);
+        assert_eq!(state.polygon_mode, PolygonMode::Fill);
     }
 
     #[test]
@@ -1790,11 +2178,23 @@ mod tests {
         let state = PrimitiveState::new()
             .with_topology(PrimitiveTopology::LineList)
             .with_cull_mode(CullMode::Back)
-            .with_front_face(FrontFace::Cw);
+            .with_front_face(FrontFace::Cw)
+            .with_polygon_mode(PolygonMode::Line);
 
         assert_eq!(state.topology, PrimitiveTopology::LineList);
         assert_eq!(state.cull_mode, CullMode::Back);
         assert_eq!(state.front_face, FrontFace::Cw);
+        assert_eq!(state.polygon_mode, PolygonMode::Line);
+        assert_eq!(state.to_wgpu().polygon_mode, wgpu::PolygonMode::Line);
+    }
+
+    #[test]
+    fn test_polygon_mode_features() {
+        assert!(PolygonMode::Fill.is_supported(wgpu::Features::empty()));
+        assert!(!PolygonMode::Line.is_supported(wgpu::Features::empty()));
+        assert!(PolygonMode::Line.is_supported(wgpu::Features::POLYGON_MODE_LINE));
+        assert!(!PolygonMode::Point.is_supported(wgpu::Features::POLYGON_MODE_LINE));
+        assert!(PolygonMode::Point.is_supported(wgpu::Features::POLYGON_MODE_POINT));
     }
 
     #[test]
@@ -1900,6 +2300,119 @@ mod tests {
         let cache = PipelineCache::new();
         assert_eq!(cache.len(), 0);
         assert!(cache.is_empty());
-        assert!(!cache.contains("test"));
+        assert!(!cache.contains(&key(0)));
+        assert_eq!(cache.capacity(), DEFAULT_PIPELINE_CACHE_CAPACITY);
+        assert_eq!(cache.stats().hit_rate(), None);
+    }
+
+    fn shader(source: &str) -> ShaderModule {
+        ShaderModule::from_source(source, None).unwrap()
+    }
+
+    /// A distinct key per `n`
+    fn key(n: u32) -> PipelineKey {
+        let descriptor =
+            RenderPipelineDescriptor::new(None).with_vertex_entry_point(&format!("main_{}", n));
+        PipelineKey::new(&descriptor, &shader("@vertex fn main() {}"), None)
+    }
+
+    #[test]
+    fn test_pipeline_key_covers_the_descriptor() {
+        let vertex = shader("@vertex fn main() {}");
+        let fragment = shader("@fragment fn main() {}");
+        let base = RenderPipelineDescriptor::new(Some("a"))
+            .with_fragment_target(ColorTargetState::new(wgpu::TextureFormat::Rgba8Unorm));
+        let key_of = |descriptor: &RenderPipelineDescriptor| {
+            PipelineKey::new(descriptor, &vertex, Some(&fragment))
+        };
+
+        // The label is only a name
+        let relabeled = RenderPipelineDescriptor {
+            label: Some("b".to_string()),
+            ..base.clone()
+        };
+        assert_eq!(key_of(&base), key_of(&relabeled));
+
+        let variants = [
+            base.clone()
+                .with_primitive(PrimitiveState::new().with_cull_mode(CullMode::Back)),
+            base.clone()
+                .with_multisample(MultisampleState::new().with_count(4)),
+            base.clone()
+                .with_depth_stencil(DepthStencilState::new(wgpu::TextureFormat::Depth32Float)),
+            base.clone().with_depth_stencil(DepthStencilState {
+                depth_bias_slope_scale: 1.5,
+                ..DepthStencilState::new(wgpu::TextureFormat::Depth32Float)
+            }),
+            base.clone().with_vertex_buffer(
+                VertexBufferLayout::new(12, VertexStepMode::Vertex)
+                    .with_attribute(VertexAttribute::new(0, VertexFormat::Float32x3, 0)),
+            ),
+            base.clone().with_fragment_target(
+                ColorTargetState::new(wgpu::TextureFormat::Rgba8Unorm)
+                    .with_blend(BlendState::alpha_blending()),
+            ),
+            base.clone().with_fragment_entry_point("fs_main"),
+        ];
+        let mut keys: Vec<PipelineKey> = variants.iter().map(key_of).collect();
+        keys.push(key_of(&base));
+        for (i, a) in keys.iter().enumerate() {
+            for b in &keys[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+
+        // Shaders count by source
+        let other = shader("@fragment fn main() { discard; }");
+        assert_ne!(
+            key_of(&base),
+            PipelineKey::new(&base, &vertex, Some(&other))
+        );
+        assert_ne!(key_of(&base), PipelineKey::new(&base, &vertex, None));
+        assert!(key_of(&base).uses_shader(fragment.source_hash()));
+        assert!(!key_of(&base).uses_shader(other.source_hash()));
+    }
+
+    #[test]
+    fn test_lru_cache_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert(key(1), 1);
+        cache.insert(key(2), 2);
+        // Touching 1 makes 2 the oldest
+        assert_eq!(cache.get(&key(1)), Some(1));
+        cache.insert(key(3), 3);
+        assert!(cache.entries.contains_key(&key(1)));
+        assert!(!cache.entries.contains_key(&key(2)));
+        assert!(cache.entries.contains_key(&key(3)));
+
+        assert_eq!(cache.get(&key(2)), None);
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 1, 1));
+        assert_eq!(stats.hit_rate(), Some(0.5));
+
+        cache.set_capacity(1);
+        assert_eq!(cache.entries.len(), 1);
+        assert!(cache.entries.contains_key(&key(3)));
+        cache.set_capacity(0);
+        assert_eq!(cache.stats().capacity, 1);
+    }
+
+    #[test]
+    fn test_lru_cache_invalidates_by_shader() {
+        let vertex = shader("@vertex fn main() {}");
+        let first = shader("@fragment fn main() {}");
+        let second = shader("@fragment fn main() { discard; }");
+        let descriptor = RenderPipelineDescriptor::new(None);
+        let mut cache = LruCache::new(8);
+        cache.insert(PipelineKey::new(&descriptor, &vertex, Some(&first)), 1);
+        cache.insert(PipelineKey::new(&descriptor, &vertex, Some(&second)), 2);
+
+        assert_eq!(cache.invalidate_shader(first.source_hash()), 1);
+        assert_eq!(cache.entries.len(), 1);
+        assert_eq!(cache.invalidate_shader(vertex.source_hash()), 1);
+        assert!(cache.entries.is_empty());
+        assert_eq!(cache.stats().invalidations, 2);
+        assert_eq!(cache.stats().evictions, 0);
     }
 }
@@ -1,14 +1,89 @@
+use crate::blend_visualizer::BlendVisualizer;
+use crate::camera::Camera;
+use crate::device_recovery::ResourceRecreate;
+use crate::i18n::{tr, tr_args};
+use crate::operation_errors::OperationErrors;
+use crate::pipeline_diff::{self, DescriptorDiff};
 use crate::pipeline_preview::RenderPipelinePreviewState;
 use crate::render_pipeline::{
     BlendComponent, BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrites,
-    CompareFunction, CullMode, DepthStencilState, FrontFace, MultisampleState, PrimitiveState,
-    PrimitiveTopology, RenderPipelineDescriptor, StencilFaceState, StencilOperation,
+    CompareFunction, CullMode, DepthStencilState, FrontFace, MultisampleState, PolygonMode,
+    PipelineCache, PrimitiveState, PrimitiveTopology, RenderPipelineDescriptor, StencilFaceState,
+    StencilOperation,
 };
+use crate::scene::{Scene, SceneEditor};
+use crate::state::{parse_variant, RenderPipelinePanelState};
+use crate::stencil_demo_panel::StencilDemoPanel;
 use crate::tooltip::{
-    blend_factor, blend_operation, compare_function, cull_mode, front_face, primitive_topology,
-    property, stencil_operation,
+    blend_factor, blend_operation, compare_function, cull_mode, front_face, polygon_mode,
+    primitive_topology, property, stencil_operation,
 };
 
+/// Operation name of creating the previews' resources in [`OperationErrors`]
+#[cfg(not(target_arch = "wasm32"))]
+const PREVIEW_SETUP: &str = "preview_setup";
+
+/// Operation name of building the current preview's pipeline in [`OperationErrors`]
+#[cfg(not(target_arch = "wasm32"))]
+const PREVIEW_PIPELINE: &str = "preview_pipeline";
+
+/// Operation name of building the baseline preview's pipeline in [`OperationErrors`]
+#[cfg(not(target_arch = "wasm32"))]
+const BASELINE_PIPELINE: &str = "baseline_pipeline";
+
+const TOPOLOGIES: [PrimitiveTopology; 5] = [
+    PrimitiveTopology::PointList,
+    PrimitiveTopology::LineList,
+    PrimitiveTopology::LineStrip,
+    PrimitiveTopology::TriangleList,
+    PrimitiveTopology::TriangleStrip,
+];
+const CULL_MODES: [CullMode; 3] = [CullMode::None, CullMode::Front, CullMode::Back];
+const FRONT_FACES: [FrontFace; 2] = [FrontFace::Ccw, FrontFace::Cw];
+const POLYGON_MODES: [PolygonMode; 3] = [PolygonMode::Fill, PolygonMode::Line, PolygonMode::Point];
+const COMPARE_FUNCTIONS: [CompareFunction; 8] = [
+    CompareFunction::Never,
+    CompareFunction::Less,
+    CompareFunction::Equal,
+    CompareFunction::LessEqual,
+    CompareFunction::Greater,
+    CompareFunction::NotEqual,
+    CompareFunction::GreaterEqual,
+    CompareFunction::Always,
+];
+const STENCIL_OPERATIONS: [StencilOperation; 8] = [
+    StencilOperation::Keep,
+    StencilOperation::Zero,
+    StencilOperation::Replace,
+    StencilOperation::IncrementClamp,
+    StencilOperation::DecrementClamp,
+    StencilOperation::Invert,
+    StencilOperation::IncrementWrap,
+    StencilOperation::DecrementWrap,
+];
+const BLEND_FACTORS: [BlendFactor; 13] = [
+    BlendFactor::Zero,
+    BlendFactor::One,
+    BlendFactor::Src,
+    BlendFactor::OneMinusSrc,
+    BlendFactor::SrcAlpha,
+    BlendFactor::OneMinusSrcAlpha,
+    BlendFactor::Dst,
+    BlendFactor::OneMinusDst,
+    BlendFactor::DstAlpha,
+    BlendFactor::OneMinusDstAlpha,
+    BlendFactor::Constant,
+    BlendFactor::OneMinusConstant,
+    BlendFactor::SrcAlphaSaturated,
+];
+const BLEND_OPERATIONS: [BlendOperation; 5] = [
+    BlendOperation::Add,
+    BlendOperation::Subtract,
+    BlendOperation::ReverseSubtract,
+    BlendOperation::Min,
+    BlendOperation::Max,
+];
+
 /// UI panel for configuring render pipelines
 pub struct RenderPipelinePanel {
     /// Current pipeline descriptor being configured
@@ -29,6 +104,10 @@ pub struct RenderPipelinePanel {
     cull_mode: CullMode,
     /// Front face
     front_face: FrontFace,
+    /// Polygon mode
+    polygon_mode: PolygonMode,
+    /// Features of the device, which gate the non-fill polygon modes
+    device_features: wgpu::Features,
 
     // Depth-Stencil State
     /// Whether depth-stencil is enabled
@@ -83,6 +162,8 @@ pub struct RenderPipelinePanel {
     alpha_blend_dst: BlendFactor,
     /// Alpha blend operation
     alpha_blend_op: BlendOperation,
+    /// Truth table of the blend settings above
+    blend_visualizer: BlendVisualizer,
     /// Color write red
     write_red: bool,
     /// Color write green
@@ -101,6 +182,26 @@ pub struct RenderPipelinePanel {
     preview_state: Option<RenderPipelinePreviewState>,
     /// Whether preview is enabled
     show_preview: bool,
+    /// Camera shared by the current and baseline previews
+    preview_camera: Camera,
+    /// Scene drawn by both previews
+    preview_scene: Scene,
+    /// Tree view for editing the preview scene
+    scene_editor: SceneEditor,
+
+    /// Snapshotted descriptor for A/B comparison
+    baseline: Option<RenderPipelineDescriptor>,
+    /// Preview rendering state for the baseline configuration
+    baseline_preview_state: Option<RenderPipelinePreviewState>,
+    /// Whether the diff table hides unchanged fields
+    diff_only_changed: bool,
+    /// Compiled pipelines of both previews
+    pipeline_cache: PipelineCache,
+    /// Device errors from building the previews and their pipelines
+    gpu_errors: OperationErrors,
+
+    /// Stencil buffer step-through driven by the stencil front face settings
+    stencil_demo: StencilDemoPanel,
 }
 
 /// Depth format options for UI
@@ -204,6 +305,8 @@ impl RenderPipelinePanel {
             topology: PrimitiveTopology::TriangleList,
             cull_mode: CullMode::None,
             front_face: FrontFace::Ccw,
+            polygon_mode: PolygonMode::Fill,
+            device_features: wgpu::Features::empty(),
 
             // Depth-Stencil State
             enable_depth_stencil: false,
@@ -234,6 +337,7 @@ impl RenderPipelinePanel {
             alpha_blend_src: BlendFactor::One,
             alpha_blend_dst: BlendFactor::Zero,
             alpha_blend_op: BlendOperation::Add,
+            blend_visualizer: BlendVisualizer::new(),
             write_red: true,
             write_green: true,
             write_blue: true,
@@ -244,19 +348,243 @@ impl RenderPipelinePanel {
 
             preview_state: None,
             show_preview: false,
+            preview_camera: Camera::new(),
+            preview_scene: Scene::default(),
+            scene_editor: SceneEditor::new(),
+
+            baseline: None,
+            baseline_preview_state: None,
+            diff_only_changed: true,
+            pipeline_cache: PipelineCache::new(),
+            gpu_errors: OperationErrors::new(),
+
+            stencil_demo: StencilDemoPanel::new(),
+        }
+    }
+
+    /// Reset all settings to their defaults, keeping the A/B baseline
+    fn reset(&mut self) {
+        let baseline = self.baseline.take();
+        let baseline_preview_state = self.baseline_preview_state.take();
+        let device_features = self.device_features;
+        let preview_scene = std::mem::take(&mut self.preview_scene);
+        let scene_editor = std::mem::take(&mut self.scene_editor);
+        let pipeline_cache = self.pipeline_cache.clone();
+        *self = Self::new();
+        self.pipeline_cache = pipeline_cache;
+        self.baseline = baseline;
+        self.baseline_preview_state = baseline_preview_state;
+        self.preview_scene = preview_scene;
+        self.scene_editor = scene_editor;
+        self.device_features = device_features;
+    }
+
+    /// Set the features of the device pipelines are created on
+    ///
+    /// The line and point polygon modes are only offered when the device has
+    /// the matching feature.
+    pub fn set_device_features(&mut self, features: wgpu::Features) {
+        self.device_features = features;
+    }
+
+    /// Snapshot the current configuration as the A/B baseline
+    pub fn snapshot_baseline(&mut self) {
+        self.update_descriptor();
+        self.baseline = Some(self.descriptor.clone());
+        // Restart both previews so A and B animate in lockstep
+        self.baseline_preview_state = None;
+        if let Some(preview) = &mut self.preview_state {
+            preview.set_time(0.0);
+        }
+        self.success_message = Some(tr("render-pipeline-ab-snapshot-taken"));
+    }
+
+    /// Drop the A/B baseline
+    pub fn clear_baseline(&mut self) {
+        self.baseline = None;
+        self.baseline_preview_state = None;
+    }
+
+    /// The snapshotted baseline, if any
+    pub fn baseline(&self) -> Option<&RenderPipelineDescriptor> {
+        self.baseline.as_ref()
+    }
+
+    /// Compare the baseline with the current settings
+    pub fn baseline_diff(&mut self) -> Option<DescriptorDiff> {
+        self.update_descriptor();
+        self.baseline
+            .as_ref()
+            .map(|baseline| pipeline_diff::diff(baseline, &self.descriptor))
+    }
+
+    /// Export the current state to a serializable format
+    pub fn export_state(&self) -> RenderPipelinePanelState {
+        RenderPipelinePanelState {
+            label: self.label_input.clone(),
+            vertex_entry_point: self.vertex_entry_point.clone(),
+            fragment_entry_point: self.fragment_entry_point.clone(),
+            topology: format!("{:?}", self.topology),
+            cull_mode: format!("{:?}", self.cull_mode),
+            front_face: format!("{:?}", self.front_face),
+            polygon_mode: format!("{:?}", self.polygon_mode),
+            enable_depth_stencil: self.enable_depth_stencil,
+            depth_format: format!("{:?}", self.depth_format),
+            depth_write_enabled: self.depth_write_enabled,
+            depth_compare: format!("{:?}", self.depth_compare),
+            stencil_read_mask: self.stencil_read_mask_input.clone(),
+            stencil_write_mask: self.stencil_write_mask_input.clone(),
+            stencil_front_compare: format!("{:?}", self.stencil_front_compare),
+            stencil_front_fail_op: format!("{:?}", self.stencil_front_fail_op),
+            stencil_front_depth_fail_op: format!("{:?}", self.stencil_front_depth_fail_op),
+            stencil_front_pass_op: format!("{:?}", self.stencil_front_pass_op),
+            stencil_back_compare: format!("{:?}", self.stencil_back_compare),
+            stencil_back_fail_op: format!("{:?}", self.stencil_back_fail_op),
+            stencil_back_depth_fail_op: format!("{:?}", self.stencil_back_depth_fail_op),
+            stencil_back_pass_op: format!("{:?}", self.stencil_back_pass_op),
+            sample_count: self.sample_count,
+            alpha_to_coverage_enabled: self.alpha_to_coverage_enabled,
+            target_format: format!("{:?}", self.target_format),
+            blend_enabled: self.blend_enabled,
+            color_blend_src: format!("{:?}", self.color_blend_src),
+            color_blend_dst: format!("{:?}", self.color_blend_dst),
+            color_blend_op: format!("{:?}", self.color_blend_op),
+            alpha_blend_src: format!("{:?}", self.alpha_blend_src),
+            alpha_blend_dst: format!("{:?}", self.alpha_blend_dst),
+            alpha_blend_op: format!("{:?}", self.alpha_blend_op),
+            write_red: self.write_red,
+            write_green: self.write_green,
+            write_blue: self.write_blue,
+            write_alpha: self.write_alpha,
         }
     }
 
+    /// Import state from a serializable format
+    ///
+    /// Enum fields with unknown names keep their current values.
+    pub fn import_state(&mut self, state: &RenderPipelinePanelState) {
+        fn set<T: std::fmt::Debug + Copy>(field: &mut T, name: &str, options: &[T]) {
+            if let Some(value) = parse_variant(name, options) {
+                *field = value;
+            }
+        }
+
+        self.label_input = state.label.clone();
+        self.vertex_entry_point = state.vertex_entry_point.clone();
+        self.fragment_entry_point = state.fragment_entry_point.clone();
+        set(&mut self.topology, &state.topology, &TOPOLOGIES);
+        set(&mut self.cull_mode, &state.cull_mode, &CULL_MODES);
+        set(&mut self.front_face, &state.front_face, &FRONT_FACES);
+        set(&mut self.polygon_mode, &state.polygon_mode, &POLYGON_MODES);
+
+        self.enable_depth_stencil = state.enable_depth_stencil;
+        set(
+            &mut self.depth_format,
+            &state.depth_format,
+            &DepthFormat::all(),
+        );
+        self.depth_write_enabled = state.depth_write_enabled;
+        set(
+            &mut self.depth_compare,
+            &state.depth_compare,
+            &COMPARE_FUNCTIONS,
+        );
+        self.stencil_read_mask_input = state.stencil_read_mask.clone();
+        self.stencil_write_mask_input = state.stencil_write_mask.clone();
+        for (compare, fail_op, depth_fail_op, pass_op, saved) in [
+            (
+                &mut self.stencil_front_compare,
+                &mut self.stencil_front_fail_op,
+                &mut self.stencil_front_depth_fail_op,
+                &mut self.stencil_front_pass_op,
+                [
+                    &state.stencil_front_compare,
+                    &state.stencil_front_fail_op,
+                    &state.stencil_front_depth_fail_op,
+                    &state.stencil_front_pass_op,
+                ],
+            ),
+            (
+                &mut self.stencil_back_compare,
+                &mut self.stencil_back_fail_op,
+                &mut self.stencil_back_depth_fail_op,
+                &mut self.stencil_back_pass_op,
+                [
+                    &state.stencil_back_compare,
+                    &state.stencil_back_fail_op,
+                    &state.stencil_back_depth_fail_op,
+                    &state.stencil_back_pass_op,
+                ],
+            ),
+        ] {
+            set(compare, saved[0], &COMPARE_FUNCTIONS);
+            set(fail_op, saved[1], &STENCIL_OPERATIONS);
+            set(depth_fail_op, saved[2], &STENCIL_OPERATIONS);
+            set(pass_op, saved[3], &STENCIL_OPERATIONS);
+        }
+
+        if [1, 2, 4, 8].contains(&state.sample_count) {
+            self.sample_count = state.sample_count;
+        }
+        self.alpha_to_coverage_enabled = state.alpha_to_coverage_enabled;
+
+        set(
+            &mut self.target_format,
+            &state.target_format,
+            &TargetFormat::all(),
+        );
+        self.blend_enabled = state.blend_enabled;
+        set(
+            &mut self.color_blend_src,
+            &state.color_blend_src,
+            &BLEND_FACTORS,
+        );
+        set(
+            &mut self.color_blend_dst,
+            &state.color_blend_dst,
+            &BLEND_FACTORS,
+        );
+        set(
+            &mut self.color_blend_op,
+            &state.color_blend_op,
+            &BLEND_OPERATIONS,
+        );
+        set(
+            &mut self.alpha_blend_src,
+            &state.alpha_blend_src,
+            &BLEND_FACTORS,
+        );
+        set(
+            &mut self.alpha_blend_dst,
+            &state.alpha_blend_dst,
+            &BLEND_FACTORS,
+        );
+        set(
+            &mut self.alpha_blend_op,
+            &state.alpha_blend_op,
+            &BLEND_OPERATIONS,
+        );
+        self.write_red = state.write_red;
+        self.write_green = state.write_green;
+        self.write_blue = state.write_blue;
+        self.write_alpha = state.write_alpha;
+
+        self.update_descriptor();
+        self.validation_error = None;
+        self.success_message = None;
+    }
+
     /// Apply a preset configuration
     pub fn apply_preset(&mut self, preset: PipelinePreset) {
         match preset {
             PipelinePreset::Default => {
-                *self = Self::new();
+                self.reset();
             }
             PipelinePreset::BasicTriangle => {
                 self.topology = PrimitiveTopology::TriangleList;
                 self.cull_mode = CullMode::None;
                 self.front_face = FrontFace::Ccw;
+                self.polygon_mode = PolygonMode::Fill;
                 self.enable_depth_stencil = false;
                 self.blend_enabled = false;
                 self.sample_count = 1;
@@ -265,6 +593,7 @@ impl RenderPipelinePanel {
                 self.topology = PrimitiveTopology::TriangleList;
                 self.cull_mode = CullMode::Back;
                 self.front_face = FrontFace::Ccw;
+                self.polygon_mode = PolygonMode::Fill;
                 self.enable_depth_stencil = true;
                 self.depth_write_enabled = true;
                 self.depth_compare = CompareFunction::Less;
@@ -275,6 +604,7 @@ impl RenderPipelinePanel {
                 self.topology = PrimitiveTopology::TriangleList;
                 self.cull_mode = CullMode::None;
                 self.front_face = FrontFace::Ccw;
+                self.polygon_mode = PolygonMode::Fill;
                 self.enable_depth_stencil = false;
                 self.blend_enabled = true;
                 // Premultiplied alpha blending
@@ -287,7 +617,15 @@ impl RenderPipelinePanel {
                 self.sample_count = 1;
             }
             PipelinePreset::Wireframe => {
-                self.topology = PrimitiveTopology::LineList;
+                // Without the feature, fall back to drawing the triangle
+                // indices as line pairs, which misses some edges
+                if PolygonMode::Line.is_supported(self.device_features) {
+                    self.topology = PrimitiveTopology::TriangleList;
+                    self.polygon_mode = PolygonMode::Line;
+                } else {
+                    self.topology = PrimitiveTopology::LineList;
+                    self.polygon_mode = PolygonMode::Fill;
+                }
                 self.cull_mode = CullMode::None;
                 self.front_face = FrontFace::Ccw;
                 self.enable_depth_stencil = true;
@@ -300,6 +638,7 @@ impl RenderPipelinePanel {
                 self.topology = PrimitiveTopology::TriangleList;
                 self.cull_mode = CullMode::Back;
                 self.front_face = FrontFace::Ccw;
+                self.polygon_mode = PolygonMode::Fill;
                 self.enable_depth_stencil = true;
                 self.depth_write_enabled = true;
                 self.depth_compare = CompareFunction::Less;
@@ -311,7 +650,47 @@ impl RenderPipelinePanel {
 
         self.update_descriptor();
         self.validation_error = None;
-        self.success_message = Some(format!("✓ Preset '{}' applied", preset.name()));
+        self.success_message = Some(tr_args(
+            "render-pipeline-preset-applied",
+            &[("preset", &preset.name())],
+        ));
+    }
+
+    /// Blend state built from the color and alpha settings
+    fn blend_state(&self) -> BlendState {
+        BlendState::new(
+            BlendComponent::new(
+                self.color_blend_src,
+                self.color_blend_dst,
+                self.color_blend_op,
+            ),
+            BlendComponent::new(
+                self.alpha_blend_src,
+                self.alpha_blend_dst,
+                self.alpha_blend_op,
+            ),
+        )
+    }
+
+    /// Stencil front face state built from the front face settings
+    fn stencil_front(&self) -> StencilFaceState {
+        StencilFaceState {
+            compare: self.stencil_front_compare,
+            fail_op: self.stencil_front_fail_op,
+            depth_fail_op: self.stencil_front_depth_fail_op,
+            pass_op: self.stencil_front_pass_op,
+        }
+    }
+
+    /// Stencil read and write masks parsed from their hex inputs
+    fn stencil_masks(&self) -> (u32, u32) {
+        let parse = |input: &str| {
+            u32::from_str_radix(input.trim_start_matches("0x"), 16).unwrap_or(0xFFFFFFFF)
+        };
+        (
+            parse(&self.stencil_read_mask_input),
+            parse(&self.stencil_write_mask_input),
+        )
     }
 
     /// Update the internal descriptor based on current UI state
@@ -329,7 +708,8 @@ impl RenderPipelinePanel {
                 PrimitiveState::new()
                     .with_topology(self.topology)
                     .with_cull_mode(self.cull_mode)
-                    .with_front_face(self.front_face),
+                    .with_front_face(self.front_face)
+                    .with_polygon_mode(self.polygon_mode),
             )
             .with_multisample(
                 MultisampleState::new()
@@ -339,12 +719,7 @@ impl RenderPipelinePanel {
 
         // Add depth-stencil state if enabled
         if self.enable_depth_stencil {
-            let stencil_front = StencilFaceState {
-                compare: self.stencil_front_compare,
-                fail_op: self.stencil_front_fail_op,
-                depth_fail_op: self.stencil_front_depth_fail_op,
-                pass_op: self.stencil_front_pass_op,
-            };
+            let stencil_front = self.stencil_front();
 
             let stencil_back = StencilFaceState {
                 compare: self.stencil_back_compare,
@@ -353,13 +728,7 @@ impl RenderPipelinePanel {
                 pass_op: self.stencil_back_pass_op,
             };
 
-            let stencil_read_mask =
-                u32::from_str_radix(self.stencil_read_mask_input.trim_start_matches("0x"), 16)
-                    .unwrap_or(0xFFFFFFFF);
-
-            let stencil_write_mask =
-                u32::from_str_radix(self.stencil_write_mask_input.trim_start_matches("0x"), 16)
-                    .unwrap_or(0xFFFFFFFF);
+            let (stencil_read_mask, stencil_write_mask) = self.stencil_masks();
 
             let mut depth_stencil = DepthStencilState::new(self.depth_format.to_wgpu())
                 .with_depth_write_enabled(self.depth_write_enabled)
@@ -374,22 +743,7 @@ impl RenderPipelinePanel {
         }
 
         // Add fragment target
-        let blend = if self.blend_enabled {
-            Some(BlendState::new(
-                BlendComponent::new(
-                    self.color_blend_src,
-                    self.color_blend_dst,
-                    self.color_blend_op,
-                ),
-                BlendComponent::new(
-                    self.alpha_blend_src,
-                    self.alpha_blend_dst,
-                    self.alpha_blend_op,
-                ),
-            ))
-        } else {
-            None
-        };
+        let blend = self.blend_enabled.then(|| self.blend_state());
 
         let mut write_mask = ColorWrites::empty();
         if self.write_red {
@@ -420,13 +774,16 @@ impl RenderPipelinePanel {
     /// Render the render pipeline configuration UI
     pub fn ui(&mut self, ui: &mut egui::Ui) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("🎨 Render Pipeline Configuration");
-            ui.label("Configure comprehensive render pipeline settings with vertex, primitive, depth-stencil, multisample, and fragment states.");
+            ui.heading(tr("render-pipeline-heading"));
+            ui.label(tr("render-pipeline-intro"));
             ui.add_space(10.0);
 
             // Display messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("render-pipeline-error", &[("error", error)]),
+                );
                 ui.add_space(5.0);
             }
             if let Some(success) = &self.success_message {
@@ -436,8 +793,8 @@ impl RenderPipelinePanel {
 
             // Presets Section
             ui.group(|ui| {
-                ui.heading("📋 Presets");
-                ui.label("Quick configuration presets:");
+                ui.heading(tr("render-pipeline-presets-heading"));
+                ui.label(tr("render-pipeline-presets-intro"));
                 ui.add_space(5.0);
 
                 ui.horizontal_wrapped(|ui| {
@@ -453,14 +810,14 @@ impl RenderPipelinePanel {
 
             // Pipeline Properties
             ui.group(|ui| {
-                ui.heading("Pipeline Properties");
+                ui.heading(tr("render-pipeline-properties"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("pipeline_properties")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Label:");
+                        ui.label(tr("render-pipeline-label"));
                         ui.text_edit_singleline(&mut self.label_input);
                         ui.end_row();
                     });
@@ -470,15 +827,15 @@ impl RenderPipelinePanel {
 
             // Vertex State
             ui.group(|ui| {
-                ui.heading("🔺 Vertex State");
-                ui.label("Configure vertex shader entry point:");
+                ui.heading(tr("render-pipeline-vertex-heading"));
+                ui.label(tr("render-pipeline-vertex-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("vertex_state")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Vertex Entry Point:");
+                        ui.label(tr("render-pipeline-vertex-entry-point"));
                         ui.text_edit_singleline(&mut self.vertex_entry_point);
                         ui.end_row();
                     });
@@ -488,25 +845,45 @@ impl RenderPipelinePanel {
 
             // Primitive State
             ui.group(|ui| {
-                ui.heading("🔷 Primitive State");
-                ui.label("Configure primitive topology and culling:");
+                ui.heading(tr("render-pipeline-primitive-heading"));
+                ui.label(tr("render-pipeline-primitive-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("primitive_state")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        Self::topology_tooltip(ui.label("Topology:"), self.topology);
+                        Self::topology_tooltip(
+                            ui.label(tr("render-pipeline-topology")),
+                            self.topology,
+                        );
                         Self::render_topology_combo(ui, &mut self.topology);
                         ui.end_row();
 
-                        Self::cull_mode_tooltip(ui.label("Cull Mode:"), self.cull_mode);
+                        Self::cull_mode_tooltip(
+                            ui.label(tr("render-pipeline-cull-mode")),
+                            self.cull_mode,
+                        );
                         Self::render_cull_mode_combo(ui, &mut self.cull_mode);
                         ui.end_row();
 
-                        Self::front_face_tooltip(ui.label("Front Face:"), self.front_face);
+                        Self::front_face_tooltip(
+                            ui.label(tr("render-pipeline-front-face")),
+                            self.front_face,
+                        );
                         Self::render_front_face_combo(ui, &mut self.front_face);
                         ui.end_row();
+
+                        Self::polygon_mode_tooltip(
+                            ui.label(tr("render-pipeline-polygon-mode")),
+                            self.polygon_mode,
+                        );
+                        Self::render_polygon_mode_combo(
+                            ui,
+                            &mut self.polygon_mode,
+                            self.device_features,
+                        );
+                        ui.end_row();
                     });
             });
 
@@ -514,8 +891,11 @@ impl RenderPipelinePanel {
 
             // Depth-Stencil State
             ui.group(|ui| {
-                ui.heading("📏 Depth-Stencil State");
-                ui.checkbox(&mut self.enable_depth_stencil, "Enable Depth-Stencil Testing");
+                ui.heading(tr("render-pipeline-depth-stencil-heading"));
+                ui.checkbox(
+                    &mut self.enable_depth_stencil,
+                    tr("render-pipeline-depth-stencil-enable"),
+                );
                 ui.add_space(5.0);
 
                 if self.enable_depth_stencil {
@@ -523,71 +903,138 @@ impl RenderPipelinePanel {
                         .num_columns(2)
                         .spacing([10.0, 8.0])
                         .show(ui, |ui| {
-                            ui.label("Depth Format:");
+                            ui.label(tr("render-pipeline-depth-format"));
                             Self::render_depth_format_combo(ui, &mut self.depth_format);
                             ui.end_row();
 
-                            property::DEPTH_WRITE_ENABLED.apply(ui.label("Depth Write:"));
-                            ui.checkbox(&mut self.depth_write_enabled, "Enabled");
+                            property::DEPTH_WRITE_ENABLED
+                                .apply(ui.label(tr("render-pipeline-depth-write")));
+                            ui.checkbox(
+                                &mut self.depth_write_enabled,
+                                tr("render-pipeline-enabled"),
+                            );
                             ui.end_row();
 
-                            Self::compare_function_tooltip(ui.label("Depth Compare:"), self.depth_compare);
-                            Self::render_compare_function_combo(ui, &mut self.depth_compare, "depth_compare");
+                            Self::compare_function_tooltip(
+                                ui.label(tr("render-pipeline-depth-compare")),
+                                self.depth_compare,
+                            );
+                            Self::render_compare_function_combo(
+                                ui,
+                                &mut self.depth_compare,
+                                "depth_compare",
+                            );
                             ui.end_row();
 
-                            ui.label("Stencil Read Mask:");
+                            ui.label(tr("render-pipeline-stencil-read-mask"));
                             ui.text_edit_singleline(&mut self.stencil_read_mask_input);
                             ui.end_row();
 
-                            ui.label("Stencil Write Mask:");
+                            ui.label(tr("render-pipeline-stencil-write-mask"));
                             ui.text_edit_singleline(&mut self.stencil_write_mask_input);
                             ui.end_row();
                         });
 
                     ui.add_space(5.0);
 
-                    ui.collapsing("Stencil Front Face", |ui| {
+                    ui.collapsing(tr("render-pipeline-stencil-front"), |ui| {
                         egui::Grid::new("stencil_front")
                             .num_columns(2)
                             .spacing([10.0, 8.0])
                             .show(ui, |ui| {
-                                Self::compare_function_tooltip(ui.label("Compare:"), self.stencil_front_compare);
-                                Self::render_compare_function_combo(ui, &mut self.stencil_front_compare, "stencil_front_compare");
+                                Self::compare_function_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-compare")),
+                                    self.stencil_front_compare,
+                                );
+                                Self::render_compare_function_combo(
+                                    ui,
+                                    &mut self.stencil_front_compare,
+                                    "stencil_front_compare",
+                                );
                                 ui.end_row();
 
-                                Self::stencil_operation_tooltip(ui.label("Fail Operation:"), self.stencil_front_fail_op);
-                                Self::render_stencil_operation_combo(ui, &mut self.stencil_front_fail_op, "stencil_front_fail");
+                                Self::stencil_operation_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-fail")),
+                                    self.stencil_front_fail_op,
+                                );
+                                Self::render_stencil_operation_combo(
+                                    ui,
+                                    &mut self.stencil_front_fail_op,
+                                    "stencil_front_fail",
+                                );
                                 ui.end_row();
 
-                                Self::stencil_operation_tooltip(ui.label("Depth Fail Operation:"), self.stencil_front_depth_fail_op);
-                                Self::render_stencil_operation_combo(ui, &mut self.stencil_front_depth_fail_op, "stencil_front_depth_fail");
+                                Self::stencil_operation_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-depth-fail")),
+                                    self.stencil_front_depth_fail_op,
+                                );
+                                Self::render_stencil_operation_combo(
+                                    ui,
+                                    &mut self.stencil_front_depth_fail_op,
+                                    "stencil_front_depth_fail",
+                                );
                                 ui.end_row();
 
-                                Self::stencil_operation_tooltip(ui.label("Pass Operation:"), self.stencil_front_pass_op);
-                                Self::render_stencil_operation_combo(ui, &mut self.stencil_front_pass_op, "stencil_front_pass");
+                                Self::stencil_operation_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-pass")),
+                                    self.stencil_front_pass_op,
+                                );
+                                Self::render_stencil_operation_combo(
+                                    ui,
+                                    &mut self.stencil_front_pass_op,
+                                    "stencil_front_pass",
+                                );
                                 ui.end_row();
                             });
                     });
 
-                    ui.collapsing("Stencil Back Face", |ui| {
+                    ui.collapsing(tr("render-pipeline-stencil-back"), |ui| {
                         egui::Grid::new("stencil_back")
                             .num_columns(2)
                             .spacing([10.0, 8.0])
                             .show(ui, |ui| {
-                                Self::compare_function_tooltip(ui.label("Compare:"), self.stencil_back_compare);
-                                Self::render_compare_function_combo(ui, &mut self.stencil_back_compare, "stencil_back_compare");
+                                Self::compare_function_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-compare")),
+                                    self.stencil_back_compare,
+                                );
+                                Self::render_compare_function_combo(
+                                    ui,
+                                    &mut self.stencil_back_compare,
+                                    "stencil_back_compare",
+                                );
                                 ui.end_row();
 
-                                Self::stencil_operation_tooltip(ui.label("Fail Operation:"), self.stencil_back_fail_op);
-                                Self::render_stencil_operation_combo(ui, &mut self.stencil_back_fail_op, "stencil_back_fail");
+                                Self::stencil_operation_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-fail")),
+                                    self.stencil_back_fail_op,
+                                );
+                                Self::render_stencil_operation_combo(
+                                    ui,
+                                    &mut self.stencil_back_fail_op,
+                                    "stencil_back_fail",
+                                );
                                 ui.end_row();
 
-                                Self::stencil_operation_tooltip(ui.label("Depth Fail Operation:"), self.stencil_back_depth_fail_op);
-                                Self::render_stencil_operation_combo(ui, &mut self.stencil_back_depth_fail_op, "stencil_back_depth_fail");
+                                Self::stencil_operation_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-depth-fail")),
+                                    self.stencil_back_depth_fail_op,
+                                );
+                                Self::render_stencil_operation_combo(
+                                    ui,
+                                    &mut self.stencil_back_depth_fail_op,
+                                    "stencil_back_depth_fail",
+                                );
                                 ui.end_row();
 
-                                Self::stencil_operation_tooltip(ui.label("Pass Operation:"), self.stencil_back_pass_op);
-                                Self::render_stencil_operation_combo(ui, &mut self.stencil_back_pass_op, "stencil_back_pass");
+                                Self::stencil_operation_tooltip(
+                                    ui.label(tr("render-pipeline-stencil-pass")),
+                                    self.stencil_back_pass_op,
+                                );
+                                Self::render_stencil_operation_combo(
+                                    ui,
+                                    &mut self.stencil_back_pass_op,
+                                    "stencil_back_pass",
+                                );
                                 ui.end_row();
                             });
                     });
@@ -598,15 +1045,15 @@ impl RenderPipelinePanel {
 
             // Multisample State
             ui.group(|ui| {
-                ui.heading("🔬 Multisample State");
-                ui.label("Configure multisampling anti-aliasing:");
+                ui.heading(tr("render-pipeline-multisample-heading"));
+                ui.label(tr("render-pipeline-multisample-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("multisample_state")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        property::SAMPLE_COUNT.apply(ui.label("Sample Count:"));
+                        property::SAMPLE_COUNT.apply(ui.label(tr("render-pipeline-sample-count")));
                         egui::ComboBox::from_id_salt("sample_count")
                             .selected_text(format!("{}", self.sample_count))
                             .show_ui(ui, |ui| {
@@ -617,8 +1064,12 @@ impl RenderPipelinePanel {
                             });
                         ui.end_row();
 
-                        property::ALPHA_TO_COVERAGE.apply(ui.label("Alpha to Coverage:"));
-                        ui.checkbox(&mut self.alpha_to_coverage_enabled, "Enabled");
+                        property::ALPHA_TO_COVERAGE
+                            .apply(ui.label(tr("render-pipeline-alpha-to-coverage")));
+                        ui.checkbox(
+                            &mut self.alpha_to_coverage_enabled,
+                            tr("render-pipeline-enabled"),
+                        );
                         ui.end_row();
                     });
             });
@@ -627,77 +1078,127 @@ impl RenderPipelinePanel {
 
             // Fragment State
             ui.group(|ui| {
-                ui.heading("🎨 Fragment State");
-                ui.label("Configure fragment shader and color output:");
+                ui.heading(tr("render-pipeline-fragment-heading"));
+                ui.label(tr("render-pipeline-fragment-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("fragment_state")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Fragment Entry Point:");
+                        ui.label(tr("render-pipeline-fragment-entry-point"));
                         ui.text_edit_singleline(&mut self.fragment_entry_point);
                         ui.end_row();
 
-                        ui.label("Target Format:");
+                        ui.label(tr("render-pipeline-target-format"));
                         Self::render_target_format_combo(ui, &mut self.target_format);
                         ui.end_row();
 
-                        ui.label("Blending:");
-                        ui.checkbox(&mut self.blend_enabled, "Enable Blending");
+                        ui.label(tr("render-pipeline-blending"));
+                        ui.checkbox(
+                            &mut self.blend_enabled,
+                            tr("render-pipeline-blending-enable"),
+                        );
                         ui.end_row();
                     });
 
                 if self.blend_enabled {
                     ui.add_space(5.0);
 
-                    ui.collapsing("Color Blend", |ui| {
+                    ui.collapsing(tr("render-pipeline-color-blend"), |ui| {
                         egui::Grid::new("color_blend")
                             .num_columns(2)
                             .spacing([10.0, 8.0])
                             .show(ui, |ui| {
-                                Self::blend_factor_tooltip(ui.label("Source Factor:"), self.color_blend_src);
-                                Self::render_blend_factor_combo(ui, &mut self.color_blend_src, "color_src");
+                                Self::blend_factor_tooltip(
+                                    ui.label(tr("render-pipeline-blend-src")),
+                                    self.color_blend_src,
+                                );
+                                Self::render_blend_factor_combo(
+                                    ui,
+                                    &mut self.color_blend_src,
+                                    "color_src",
+                                );
                                 ui.end_row();
 
-                                Self::blend_factor_tooltip(ui.label("Destination Factor:"), self.color_blend_dst);
-                                Self::render_blend_factor_combo(ui, &mut self.color_blend_dst, "color_dst");
+                                Self::blend_factor_tooltip(
+                                    ui.label(tr("render-pipeline-blend-dst")),
+                                    self.color_blend_dst,
+                                );
+                                Self::render_blend_factor_combo(
+                                    ui,
+                                    &mut self.color_blend_dst,
+                                    "color_dst",
+                                );
                                 ui.end_row();
 
-                                Self::blend_operation_tooltip(ui.label("Operation:"), self.color_blend_op);
-                                Self::render_blend_operation_combo(ui, &mut self.color_blend_op, "color_op");
+                                Self::blend_operation_tooltip(
+                                    ui.label(tr("render-pipeline-blend-operation")),
+                                    self.color_blend_op,
+                                );
+                                Self::render_blend_operation_combo(
+                                    ui,
+                                    &mut self.color_blend_op,
+                                    "color_op",
+                                );
                                 ui.end_row();
                             });
                     });
 
-                    ui.collapsing("Alpha Blend", |ui| {
+                    ui.collapsing(tr("render-pipeline-alpha-blend"), |ui| {
                         egui::Grid::new("alpha_blend")
                             .num_columns(2)
                             .spacing([10.0, 8.0])
                             .show(ui, |ui| {
-                                Self::blend_factor_tooltip(ui.label("Source Factor:"), self.alpha_blend_src);
-                                Self::render_blend_factor_combo(ui, &mut self.alpha_blend_src, "alpha_src");
+                                Self::blend_factor_tooltip(
+                                    ui.label(tr("render-pipeline-blend-src")),
+                                    self.alpha_blend_src,
+                                );
+                                Self::render_blend_factor_combo(
+                                    ui,
+                                    &mut self.alpha_blend_src,
+                                    "alpha_src",
+                                );
                                 ui.end_row();
 
-                                Self::blend_factor_tooltip(ui.label("Destination Factor:"), self.alpha_blend_dst);
-                                Self::render_blend_factor_combo(ui, &mut self.alpha_blend_dst, "alpha_dst");
+                                Self::blend_factor_tooltip(
+                                    ui.label(tr("render-pipeline-blend-dst")),
+                                    self.alpha_blend_dst,
+                                );
+                                Self::render_blend_factor_combo(
+                                    ui,
+                                    &mut self.alpha_blend_dst,
+                                    "alpha_dst",
+                                );
                                 ui.end_row();
 
-                                Self::blend_operation_tooltip(ui.label("Operation:"), self.alpha_blend_op);
-                                Self::render_blend_operation_combo(ui, &mut self.alpha_blend_op, "alpha_op");
+                                Self::blend_operation_tooltip(
+                                    ui.label(tr("render-pipeline-blend-operation")),
+                                    self.alpha_blend_op,
+                                );
+                                Self::render_blend_operation_combo(
+                                    ui,
+                                    &mut self.alpha_blend_op,
+                                    "alpha_op",
+                                );
                                 ui.end_row();
                             });
                     });
+
+                    let blend = self.blend_state();
+                    ui.collapsing(tr("render-pipeline-blend-visualizer"), |ui| {
+                        self.blend_visualizer.ui(ui, &blend);
+                    });
                 }
 
                 ui.add_space(5.0);
 
-                ui.label("Color Write Mask:");
+                ui.label(tr("render-pipeline-write-mask"));
                 ui.horizontal(|ui| {
-                    ui.checkbox(&mut self.write_red, "Red");
-                    ui.checkbox(&mut self.write_green, "Green");
-                    ui.checkbox(&mut self.write_blue, "Blue");
-                    ui.checkbox(&mut self.write_alpha, "Alpha");
+                    ui.checkbox(&mut self.write_red, tr("render-pipeline-write-red"));
+                    ui.checkbox(&mut self.write_green, tr("render-pipeline-write-green"));
+                    ui.checkbox(&mut self.write_blue, tr("render-pipeline-write-blue"));
+                    ui.checkbox(&mut self.write_alpha, tr("render-pipeline-write-alpha"));
                 });
             });
 
@@ -705,16 +1206,19 @@ impl RenderPipelinePanel {
 
             // Action buttons
             ui.horizontal(|ui| {
-                if ui.button("📝 Update Configuration").clicked() {
+                if ui.button(tr("render-pipeline-update")).clicked() {
                     self.update_descriptor();
                     self.validation_error = None;
-                    self.success_message = Some("✓ Configuration updated".to_string());
+                    self.success_message = Some(tr("render-pipeline-updated"));
                 }
 
-                if ui.button("🔄 Reset to Default").clicked() {
-                    *self = Self::new();
+                if ui.button(tr("render-pipeline-reset")).clicked() {
+                    self.reset();
                 }
             });
+
+            ui.add_space(10.0);
+            self.render_comparison_ui(ui);
         });
     }
 
@@ -727,14 +1231,20 @@ impl RenderPipelinePanel {
         queue: Option<&wgpu::Queue>,
         renderer: Option<&mut egui_wgpu::Renderer>,
     ) {
+        if let Some(device) = device {
+            self.device_features = device.features();
+        }
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("🎨 Render Pipeline Configuration");
-            ui.label("Configure comprehensive render pipeline settings with vertex, primitive, depth-stencil, multisample, and fragment states.");
+            ui.heading(tr("render-pipeline-heading"));
+            ui.label(tr("render-pipeline-intro"));
             ui.add_space(10.0);
 
             // Display messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("render-pipeline-error", &[("error", error)]),
+                );
                 ui.add_space(5.0);
             }
             if let Some(success) = &self.success_message {
@@ -744,8 +1254,8 @@ impl RenderPipelinePanel {
 
             // Presets Section
             ui.group(|ui| {
-                ui.heading("📋 Presets");
-                ui.label("Quick configuration presets:");
+                ui.heading(tr("render-pipeline-presets-heading"));
+                ui.label(tr("render-pipeline-presets-intro"));
                 ui.add_space(5.0);
 
                 ui.horizontal_wrapped(|ui| {
@@ -769,123 +1279,288 @@ impl RenderPipelinePanel {
             if self.show_preview {
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.heading("🎬 Pipeline Preview");
+                        ui.heading(tr("render-pipeline-preview-heading"));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if ui.small_button("✕").on_hover_text("Hide preview").clicked() {
+                            if ui
+                                .small_button("✕")
+                                .on_hover_text(tr("render-pipeline-preview-hide"))
+                                .clicked()
+                            {
                                 self.show_preview = false;
                             }
                         });
                     });
                     ui.add_space(5.0);
 
-                    ui.label("Preview shows how this pipeline configuration affects rendering of a 3D cube:");
-                    ui.label("• Topology: Triangle/Line primitives");
-                    ui.label("• Culling: Front/back face visibility");
-                    ui.label("• Depth: Z-buffer testing effect");
-                    ui.label("• Blending: Color composition");
+                    ui.label(tr("render-pipeline-preview-intro"));
+                    ui.label(tr("render-pipeline-preview-topology"));
+                    ui.label(tr("render-pipeline-preview-culling"));
+                    ui.label(tr("render-pipeline-preview-depth"));
+                    ui.label(tr("render-pipeline-preview-blending"));
 
                     ui.add_space(5.0);
 
-                    // Initialize preview if we have device
+                    if self.baseline.is_some() {
+                        ui.label(tr("render-pipeline-preview-ab"));
+                        ui.add_space(5.0);
+                    }
+
+                    // Initialize previews if we have device
                     if let Some(device) = device {
                         if self.preview_state.is_none() {
                             let mut preview = RenderPipelinePreviewState::new();
-                            preview.initialize(device);
+                            self.gpu_errors.scoped(device, PREVIEW_SETUP, || preview.initialize(device));
                             self.preview_state = Some(preview);
                         }
+                        if self.baseline.is_some() && self.baseline_preview_state.is_none() {
+                            let mut preview = RenderPipelinePreviewState::new();
+                            self.gpu_errors.scoped(device, PREVIEW_SETUP, || preview.initialize(device));
+                            self.baseline_preview_state = Some(preview);
+                        }
 
-                        // Update descriptor before borrowing preview
+                        // Update pipelines when configuration changes
                         self.update_descriptor();
-
-                        // Update pipeline when configuration changes
                         if let Some(preview) = &mut self.preview_state {
-                            // Build primitive state
-                            let primitive = PrimitiveState::new()
-                                .with_topology(self.topology)
-                                .with_cull_mode(self.cull_mode)
-                                .with_front_face(self.front_face);
-
-                            // Build depth-stencil state
-                            let depth_stencil = if self.enable_depth_stencil {
-                                Some(
-                                    DepthStencilState::new(self.depth_format.to_wgpu())
-                                        .with_depth_write_enabled(self.depth_write_enabled)
-                                        .with_depth_compare(self.depth_compare),
-                                )
-                            } else {
-                                None
-                            };
-
-                            // Build blend state
-                            let blend = if self.blend_enabled {
-                                Some(BlendState::new(
-                                    BlendComponent::new(
-                                        self.color_blend_src,
-                                        self.color_blend_dst,
-                                        self.color_blend_op,
-                                    ),
-                                    BlendComponent::new(
-                                        self.alpha_blend_src,
-                                        self.alpha_blend_dst,
-                                        self.alpha_blend_op,
-                                    ),
-                                ))
-                            } else {
-                                None
-                            };
-
-                            // Build multisample state
-                            let multisample = MultisampleState::new()
-                                .with_count(self.sample_count)
-                                .with_alpha_to_coverage(self.alpha_to_coverage_enabled);
-
-                            // Update pipeline
-                            preview.update_pipeline(
-                                device,
-                                &primitive,
-                                depth_stencil.as_ref(),
-                                blend.as_ref(),
-                                &multisample,
-                            );
+                            Self::update_preview_pipeline(preview, device, &self.pipeline_cache, &self.descriptor, &self.gpu_errors, PREVIEW_PIPELINE);
+                        }
+                        if let (Some(preview), Some(baseline)) =
+                            (&mut self.baseline_preview_state, &self.baseline)
+                        {
+                            Self::update_preview_pipeline(preview, device, &self.pipeline_cache, baseline, &self.gpu_errors, BASELINE_PIPELINE);
+                        } else {
+                            self.gpu_errors.clear(BASELINE_PIPELINE);
                         }
                     }
+                    self.gpu_errors.ui(ui, PREVIEW_SETUP);
+                    self.gpu_errors.ui(ui, PREVIEW_PIPELINE);
+                    self.gpu_errors.ui(ui, BASELINE_PIPELINE);
 
-                    // Render preview
-                    #[allow(unused_variables)]
-                    if let (Some(preview), Some(device), Some(queue), Some(renderer)) =
-                        (&mut self.preview_state, device, queue, renderer)
-                    {
-                        // Render the preview
+                    // Render previews
+                    if let (Some(device), Some(queue), Some(renderer)) = (device, queue, renderer) {
                         let delta_time = ui.input(|i| i.stable_dt);
-                        preview.render(device, queue, delta_time);
-
-                        // Display the preview texture
-                        #[cfg(not(target_arch = "wasm32"))]
-                        if let Some(texture_id) = preview.get_texture_id(device, renderer) {
-                            let (width, height) = preview.size();
-                            ui.add(egui::Image::new(egui::load::SizedTexture::new(
-                                texture_id,
-                                egui::vec2(width as f32, height as f32),
-                            )));
+                        match (&mut self.baseline_preview_state, &mut self.preview_state) {
+                            (Some(baseline), Some(current)) => {
+                                ui.horizontal(|ui| {
+                                    Self::show_preview(
+                                        ui,
+                                        Some(&tr("render-pipeline-baseline")),
+                                        baseline,
+                                        &mut self.preview_camera,
+                                        &self.preview_scene,
+                                        device,
+                                        queue,
+                                        renderer,
+                                        delta_time,
+                                    );
+                                    Self::show_preview(
+                                        ui,
+                                        Some(&tr("render-pipeline-current")),
+                                        current,
+                                        &mut self.preview_camera,
+                                        &self.preview_scene,
+                                        device,
+                                        queue,
+                                        renderer,
+                                        delta_time,
+                                    );
+                                });
+                            }
+                            (None, Some(current)) => {
+                                Self::show_preview(
+                                    ui,
+                                    None,
+                                    current,
+                                    &mut self.preview_camera,
+                                    &self.preview_scene,
+                                    device,
+                                    queue,
+                                    renderer,
+                                    delta_time,
+                                );
+                            }
+                            _ => {}
                         }
+                        self.preview_camera.ui(ui);
+                        ui.collapsing(tr("render-pipeline-preview-scene"), |ui| {
+                            self.scene_editor.ui(ui, &mut self.preview_scene);
+                        });
 
-                        // Always request repaint for animated preview (rotating cube)
+                        // Always request repaint for animated preview (rotating scene)
                         ui.ctx().request_repaint();
                     } else if device.is_none() {
                         ui.colored_label(
                             egui::Color32::YELLOW,
-                            "⚠ Preview requires GPU device to be initialized",
+                            tr("render-pipeline-preview-needs-device"),
                         );
                     }
                 });
             } else {
                 // Show "Test Pipeline" button when preview is hidden
                 ui.horizontal(|ui| {
-                    if ui.button("🎬 Test Pipeline").on_hover_text("Show live preview of pipeline configuration").clicked() {
+                    if ui
+                        .button(tr("render-pipeline-test"))
+                        .on_hover_text(tr("render-pipeline-test-hover"))
+                        .clicked()
+                    {
                         self.show_preview = true;
                     }
                 });
             }
+
+            ui.add_space(15.0);
+
+            egui::CollapsingHeader::new(tr("render-pipeline-cache"))
+                .id_salt("pipeline_cache")
+                .show(ui, |ui| self.render_pipeline_cache(ui));
+
+            ui.add_space(15.0);
+
+            egui::CollapsingHeader::new(tr("render-pipeline-stencil-demo"))
+                .id_salt("stencil_step_through")
+                .show(ui, |ui| {
+                    let (Some(device), Some(queue)) = (device, queue) else {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            tr("render-pipeline-stencil-demo-needs-device"),
+                        );
+                        return;
+                    };
+                    let mut face = self.stencil_front();
+                    let (read_mask, write_mask) = self.stencil_masks();
+                    self.stencil_demo
+                        .ui(ui, device, queue, &mut face, read_mask, write_mask);
+                    self.stencil_front_compare = face.compare;
+                    self.stencil_front_fail_op = face.fail_op;
+                    self.stencil_front_depth_fail_op = face.depth_fail_op;
+                    self.stencil_front_pass_op = face.pass_op;
+                });
+        });
+    }
+
+    /// Switch a preview to the pipeline of a descriptor, reusing a cached
+    /// one if the configuration was seen before
+    ///
+    /// Device errors of building the pipeline are attributed to `operation`.
+    /// A cached pipeline isn't validated again, so frames that keep the same
+    /// pipeline keep the error it was built with.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn update_preview_pipeline(
+        preview: &mut RenderPipelinePreviewState,
+        device: &wgpu::Device,
+        cache: &PipelineCache,
+        descriptor: &RenderPipelineDescriptor,
+        errors: &OperationErrors,
+        operation: &'static str,
+    ) {
+        let blend = descriptor
+            .fragment_targets()
+            .first()
+            .and_then(|target| target.blend);
+        let previous = preview.pipeline().cloned();
+        let previous_error = errors.get(operation);
+        errors.scoped(device, operation, || {
+            preview.update_pipeline(
+                device,
+                cache,
+                descriptor.primitive(),
+                descriptor.depth_stencil(),
+                blend.as_ref(),
+                descriptor.multisample(),
+            )
+        });
+        let unchanged = previous
+            .zip(preview.pipeline())
+            .is_some_and(|(before, after)| std::sync::Arc::ptr_eq(&before, after));
+        if let (true, Some(error)) = (unchanged, previous_error) {
+            errors.insert(operation, error);
+        }
+    }
+
+    /// Show the counters of the preview pipeline cache and its capacity
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_pipeline_cache(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("render-pipeline-cache-intro"));
+        ui.add_space(5.0);
+
+        let stats = self.pipeline_cache.stats();
+        egui::Grid::new("pipeline_cache_stats")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(tr("render-pipeline-cache-pipelines"));
+                ui.label(format!("{} / {}", stats.entries, stats.capacity));
+                ui.end_row();
+                ui.label(tr("render-pipeline-cache-hits"));
+                ui.label(stats.hits.to_string());
+                ui.end_row();
+                ui.label(tr("render-pipeline-cache-misses"));
+                ui.label(stats.misses.to_string());
+                ui.end_row();
+                ui.label(tr("render-pipeline-cache-hit-rate"));
+                ui.label(match stats.hit_rate() {
+                    Some(rate) => format!("{:.1}%", rate * 100.0),
+                    None => "-".to_string(),
+                });
+                ui.end_row();
+                ui.label(tr("render-pipeline-cache-evictions"));
+                ui.label(stats.evictions.to_string());
+                ui.end_row();
+                ui.label(tr("render-pipeline-cache-invalidations"));
+                ui.label(stats.invalidations.to_string())
+                    .on_hover_text(tr("render-pipeline-cache-invalidations-hover"));
+                ui.end_row();
+            });
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            let mut capacity = stats.capacity;
+            ui.label(tr("render-pipeline-cache-capacity"));
+            if ui.add(egui::DragValue::new(&mut capacity).range(1..=1024)).changed() {
+                self.pipeline_cache.set_capacity(capacity);
+            }
+            if ui
+                .button(tr("render-pipeline-cache-clear"))
+                .on_hover_text(tr("render-pipeline-cache-clear-hover"))
+                .clicked()
+            {
+                self.pipeline_cache.clear();
+            }
+            if ui.button(tr("render-pipeline-cache-reset")).clicked() {
+                self.pipeline_cache.reset_stats();
+            }
+        });
+    }
+
+    /// Render one preview frame and display it, optionally with a caption
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::too_many_arguments)]
+    fn show_preview(
+        ui: &mut egui::Ui,
+        caption: Option<&str>,
+        preview: &mut RenderPipelinePreviewState,
+        camera: &mut Camera,
+        scene: &Scene,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+        delta_time: f32,
+    ) {
+        preview.render(device, queue, delta_time, camera, scene);
+        ui.vertical(|ui| {
+            if let Some(caption) = caption {
+                ui.strong(caption);
+            }
+            if let Some(texture_id) = preview.get_texture_id(device, renderer) {
+                let (width, height) = preview.size();
+                let response = ui.add(
+                    egui::Image::new(egui::load::SizedTexture::new(
+                        texture_id,
+                        egui::vec2(width as f32, height as f32),
+                    ))
+                    .sense(egui::Sense::click_and_drag()),
+                );
+                camera.handle_input(ui, &response);
+            }
         });
     }
 
@@ -893,14 +1568,14 @@ impl RenderPipelinePanel {
     fn render_configuration_ui(&mut self, ui: &mut egui::Ui) {
         // Pipeline Properties
         ui.group(|ui| {
-            ui.heading("Pipeline Properties");
+            ui.heading(tr("render-pipeline-properties"));
             ui.add_space(5.0);
 
             egui::Grid::new("pipeline_properties")
                 .num_columns(2)
                 .spacing([10.0, 8.0])
                 .show(ui, |ui| {
-                    ui.label("Label:");
+                    ui.label(tr("render-pipeline-label"));
                     ui.text_edit_singleline(&mut self.label_input);
                     ui.end_row();
                 });
@@ -910,15 +1585,15 @@ impl RenderPipelinePanel {
 
         // Vertex State
         ui.group(|ui| {
-            ui.heading("🔺 Vertex State");
-            ui.label("Configure vertex shader entry point:");
+            ui.heading(tr("render-pipeline-vertex-heading"));
+            ui.label(tr("render-pipeline-vertex-intro"));
             ui.add_space(5.0);
 
             egui::Grid::new("vertex_state")
                 .num_columns(2)
                 .spacing([10.0, 8.0])
                 .show(ui, |ui| {
-                    ui.label("Vertex Entry Point:");
+                    ui.label(tr("render-pipeline-vertex-entry-point"));
                     ui.text_edit_singleline(&mut self.vertex_entry_point);
                     ui.end_row();
                 });
@@ -928,28 +1603,37 @@ impl RenderPipelinePanel {
 
         // Primitive State
         ui.group(|ui| {
-            ui.heading("🔷 Primitive State");
-            ui.label("Configure primitive topology and culling:");
+            ui.heading(tr("render-pipeline-primitive-heading"));
+            ui.label(tr("render-pipeline-primitive-intro"));
             ui.add_space(5.0);
 
             egui::Grid::new("primitive_state")
                 .num_columns(2)
                 .spacing([10.0, 8.0])
                 .show(ui, |ui| {
-                    ui.label("Topology:")
-                        .on_hover_text("How vertices are assembled into primitives");
+                    ui.label(tr("render-pipeline-topology"))
+                        .on_hover_text(tr("render-pipeline-topology-hover"));
                     Self::render_topology_combo(ui, &mut self.topology);
                     ui.end_row();
 
-                    ui.label("Cull Mode:")
-                        .on_hover_text("Which faces to cull (not render)");
+                    ui.label(tr("render-pipeline-cull-mode"))
+                        .on_hover_text(tr("render-pipeline-cull-mode-hover"));
                     Self::render_cull_mode_combo(ui, &mut self.cull_mode);
                     ui.end_row();
 
-                    ui.label("Front Face:")
-                        .on_hover_text("Winding order that determines front-facing");
+                    ui.label(tr("render-pipeline-front-face"))
+                        .on_hover_text(tr("render-pipeline-front-face-hover"));
                     Self::render_front_face_combo(ui, &mut self.front_face);
                     ui.end_row();
+
+                    ui.label(tr("render-pipeline-polygon-mode"))
+                        .on_hover_text(tr("render-pipeline-polygon-mode-hover"));
+                    Self::render_polygon_mode_combo(
+                        ui,
+                        &mut self.polygon_mode,
+                        self.device_features,
+                    );
+                    ui.end_row();
                 });
         });
 
@@ -957,10 +1641,10 @@ impl RenderPipelinePanel {
 
         // Depth-Stencil State
         ui.group(|ui| {
-            ui.heading("📏 Depth-Stencil State");
+            ui.heading(tr("render-pipeline-depth-stencil-heading"));
             ui.checkbox(
                 &mut self.enable_depth_stencil,
-                "Enable Depth-Stencil Testing",
+                tr("render-pipeline-depth-stencil-enable"),
             );
             ui.add_space(5.0);
 
@@ -969,16 +1653,16 @@ impl RenderPipelinePanel {
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Depth Format:");
+                        ui.label(tr("render-pipeline-depth-format"));
                         Self::render_depth_format_combo(ui, &mut self.depth_format);
                         ui.end_row();
 
-                        ui.label("Depth Write:");
-                        ui.checkbox(&mut self.depth_write_enabled, "Enabled");
+                        ui.label(tr("render-pipeline-depth-write"));
+                        ui.checkbox(&mut self.depth_write_enabled, tr("render-pipeline-enabled"));
                         ui.end_row();
 
-                        ui.label("Depth Compare:")
-                            .on_hover_text("Comparison function for depth test");
+                        ui.label(tr("render-pipeline-depth-compare"))
+                            .on_hover_text(tr("render-pipeline-depth-compare-hover"));
                         Self::render_compare_function_combo(
                             ui,
                             &mut self.depth_compare,
@@ -986,23 +1670,23 @@ impl RenderPipelinePanel {
                         );
                         ui.end_row();
 
-                        ui.label("Stencil Read Mask:");
+                        ui.label(tr("render-pipeline-stencil-read-mask"));
                         ui.text_edit_singleline(&mut self.stencil_read_mask_input);
                         ui.end_row();
 
-                        ui.label("Stencil Write Mask:");
+                        ui.label(tr("render-pipeline-stencil-write-mask"));
                         ui.text_edit_singleline(&mut self.stencil_write_mask_input);
                         ui.end_row();
                     });
 
                 ui.add_space(5.0);
 
-                ui.collapsing("Stencil Front Face", |ui| {
+                ui.collapsing(tr("render-pipeline-stencil-front"), |ui| {
                     egui::Grid::new("stencil_front")
                         .num_columns(2)
                         .spacing([10.0, 8.0])
                         .show(ui, |ui| {
-                            ui.label("Compare:");
+                            ui.label(tr("render-pipeline-stencil-compare"));
                             Self::render_compare_function_combo(
                                 ui,
                                 &mut self.stencil_front_compare,
@@ -1010,7 +1694,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Fail Operation:");
+                            ui.label(tr("render-pipeline-stencil-fail"));
                             Self::render_stencil_operation_combo(
                                 ui,
                                 &mut self.stencil_front_fail_op,
@@ -1018,7 +1702,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Depth Fail Operation:");
+                            ui.label(tr("render-pipeline-stencil-depth-fail"));
                             Self::render_stencil_operation_combo(
                                 ui,
                                 &mut self.stencil_front_depth_fail_op,
@@ -1026,7 +1710,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Pass Operation:");
+                            ui.label(tr("render-pipeline-stencil-pass"));
                             Self::render_stencil_operation_combo(
                                 ui,
                                 &mut self.stencil_front_pass_op,
@@ -1036,12 +1720,12 @@ impl RenderPipelinePanel {
                         });
                 });
 
-                ui.collapsing("Stencil Back Face", |ui| {
+                ui.collapsing(tr("render-pipeline-stencil-back"), |ui| {
                     egui::Grid::new("stencil_back")
                         .num_columns(2)
                         .spacing([10.0, 8.0])
                         .show(ui, |ui| {
-                            ui.label("Compare:");
+                            ui.label(tr("render-pipeline-stencil-compare"));
                             Self::render_compare_function_combo(
                                 ui,
                                 &mut self.stencil_back_compare,
@@ -1049,7 +1733,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Fail Operation:");
+                            ui.label(tr("render-pipeline-stencil-fail"));
                             Self::render_stencil_operation_combo(
                                 ui,
                                 &mut self.stencil_back_fail_op,
@@ -1057,7 +1741,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Depth Fail Operation:");
+                            ui.label(tr("render-pipeline-stencil-depth-fail"));
                             Self::render_stencil_operation_combo(
                                 ui,
                                 &mut self.stencil_back_depth_fail_op,
@@ -1065,7 +1749,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Pass Operation:");
+                            ui.label(tr("render-pipeline-stencil-pass"));
                             Self::render_stencil_operation_combo(
                                 ui,
                                 &mut self.stencil_back_pass_op,
@@ -1081,16 +1765,16 @@ impl RenderPipelinePanel {
 
         // Multisample State
         ui.group(|ui| {
-            ui.heading("🔬 Multisample State");
-            ui.label("Configure multisampling anti-aliasing:");
+            ui.heading(tr("render-pipeline-multisample-heading"));
+            ui.label(tr("render-pipeline-multisample-intro"));
             ui.add_space(5.0);
 
             egui::Grid::new("multisample_state")
                 .num_columns(2)
                 .spacing([10.0, 8.0])
                 .show(ui, |ui| {
-                    ui.label("Sample Count:")
-                        .on_hover_text("Number of samples per pixel (1, 2, 4, or 8)");
+                    ui.label(tr("render-pipeline-sample-count"))
+                        .on_hover_text(tr("render-pipeline-sample-count-hover"));
                     egui::ComboBox::from_id_salt("sample_count")
                         .selected_text(format!("{}", self.sample_count))
                         .show_ui(ui, |ui| {
@@ -1101,9 +1785,12 @@ impl RenderPipelinePanel {
                         });
                     ui.end_row();
 
-                    ui.label("Alpha to Coverage:")
-                        .on_hover_text("Enable alpha to coverage for transparency");
-                    ui.checkbox(&mut self.alpha_to_coverage_enabled, "Enabled");
+                    ui.label(tr("render-pipeline-alpha-to-coverage"))
+                        .on_hover_text(tr("render-pipeline-alpha-to-coverage-hover"));
+                    ui.checkbox(
+                        &mut self.alpha_to_coverage_enabled,
+                        tr("render-pipeline-enabled"),
+                    );
                     ui.end_row();
                 });
         });
@@ -1112,36 +1799,39 @@ impl RenderPipelinePanel {
 
         // Fragment State
         ui.group(|ui| {
-            ui.heading("🎨 Fragment State");
-            ui.label("Configure fragment shader and color output:");
+            ui.heading(tr("render-pipeline-fragment-heading"));
+            ui.label(tr("render-pipeline-fragment-intro"));
             ui.add_space(5.0);
 
             egui::Grid::new("fragment_state")
                 .num_columns(2)
                 .spacing([10.0, 8.0])
                 .show(ui, |ui| {
-                    ui.label("Fragment Entry Point:");
+                    ui.label(tr("render-pipeline-fragment-entry-point"));
                     ui.text_edit_singleline(&mut self.fragment_entry_point);
                     ui.end_row();
 
-                    ui.label("Target Format:");
+                    ui.label(tr("render-pipeline-target-format"));
                     Self::render_target_format_combo(ui, &mut self.target_format);
                     ui.end_row();
 
-                    ui.label("Blending:");
-                    ui.checkbox(&mut self.blend_enabled, "Enable Blending");
+                    ui.label(tr("render-pipeline-blending"));
+                    ui.checkbox(
+                        &mut self.blend_enabled,
+                        tr("render-pipeline-blending-enable"),
+                    );
                     ui.end_row();
                 });
 
             if self.blend_enabled {
                 ui.add_space(5.0);
 
-                ui.collapsing("Color Blend", |ui| {
+                ui.collapsing(tr("render-pipeline-color-blend"), |ui| {
                     egui::Grid::new("color_blend")
                         .num_columns(2)
                         .spacing([10.0, 8.0])
                         .show(ui, |ui| {
-                            ui.label("Source Factor:");
+                            ui.label(tr("render-pipeline-blend-src"));
                             Self::render_blend_factor_combo(
                                 ui,
                                 &mut self.color_blend_src,
@@ -1149,7 +1839,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Destination Factor:");
+                            ui.label(tr("render-pipeline-blend-dst"));
                             Self::render_blend_factor_combo(
                                 ui,
                                 &mut self.color_blend_dst,
@@ -1157,7 +1847,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Operation:");
+                            ui.label(tr("render-pipeline-blend-operation"));
                             Self::render_blend_operation_combo(
                                 ui,
                                 &mut self.color_blend_op,
@@ -1167,12 +1857,12 @@ impl RenderPipelinePanel {
                         });
                 });
 
-                ui.collapsing("Alpha Blend", |ui| {
+                ui.collapsing(tr("render-pipeline-alpha-blend"), |ui| {
                     egui::Grid::new("alpha_blend")
                         .num_columns(2)
                         .spacing([10.0, 8.0])
                         .show(ui, |ui| {
-                            ui.label("Source Factor:");
+                            ui.label(tr("render-pipeline-blend-src"));
                             Self::render_blend_factor_combo(
                                 ui,
                                 &mut self.alpha_blend_src,
@@ -1180,7 +1870,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Destination Factor:");
+                            ui.label(tr("render-pipeline-blend-dst"));
                             Self::render_blend_factor_combo(
                                 ui,
                                 &mut self.alpha_blend_dst,
@@ -1188,7 +1878,7 @@ impl RenderPipelinePanel {
                             );
                             ui.end_row();
 
-                            ui.label("Operation:");
+                            ui.label(tr("render-pipeline-blend-operation"));
                             Self::render_blend_operation_combo(
                                 ui,
                                 &mut self.alpha_blend_op,
@@ -1197,16 +1887,21 @@ impl RenderPipelinePanel {
                             ui.end_row();
                         });
                 });
+
+                let blend = self.blend_state();
+                ui.collapsing(tr("render-pipeline-blend-visualizer"), |ui| {
+                    self.blend_visualizer.ui(ui, &blend);
+                });
             }
 
             ui.add_space(5.0);
 
-            ui.label("Color Write Mask:");
+            ui.label(tr("render-pipeline-write-mask"));
             ui.horizontal(|ui| {
-                ui.checkbox(&mut self.write_red, "Red");
-                ui.checkbox(&mut self.write_green, "Green");
-                ui.checkbox(&mut self.write_blue, "Blue");
-                ui.checkbox(&mut self.write_alpha, "Alpha");
+                ui.checkbox(&mut self.write_red, tr("render-pipeline-write-red"));
+                ui.checkbox(&mut self.write_green, tr("render-pipeline-write-green"));
+                ui.checkbox(&mut self.write_blue, tr("render-pipeline-write-blue"));
+                ui.checkbox(&mut self.write_alpha, tr("render-pipeline-write-alpha"));
             });
         });
 
@@ -1214,16 +1909,108 @@ impl RenderPipelinePanel {
 
         // Action buttons
         ui.horizontal(|ui| {
-            if ui.button("📝 Update Configuration").clicked() {
+            if ui.button(tr("render-pipeline-update")).clicked() {
                 self.update_descriptor();
                 self.validation_error = None;
-                self.success_message = Some("✓ Configuration updated".to_string());
+                self.success_message = Some(tr("render-pipeline-updated"));
             }
 
-            if ui.button("🔄 Reset to Default").clicked() {
-                *self = Self::new();
+            if ui.button(tr("render-pipeline-reset")).clicked() {
+                self.reset();
             }
         });
+
+        ui.add_space(10.0);
+        self.render_comparison_ui(ui);
+    }
+
+    /// Render the A/B comparison section with the baseline diff table
+    fn render_comparison_ui(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.heading(tr("render-pipeline-ab-heading"));
+            ui.label(tr("render-pipeline-ab-intro"));
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                let snapshot_label = if self.baseline.is_some() {
+                    tr("render-pipeline-ab-resnapshot")
+                } else {
+                    tr("render-pipeline-ab-snapshot")
+                };
+                if ui
+                    .button(snapshot_label)
+                    .on_hover_text(tr("render-pipeline-ab-snapshot-hover"))
+                    .clicked()
+                {
+                    self.snapshot_baseline();
+                }
+                if self.baseline.is_some() && ui.button(tr("render-pipeline-ab-clear")).clicked() {
+                    self.clear_baseline();
+                }
+            });
+
+            let Some(diff) = self.baseline_diff() else {
+                ui.label(tr("render-pipeline-ab-none"));
+                return;
+            };
+
+            ui.add_space(5.0);
+            if diff.is_identical() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(100, 200, 100),
+                    tr("render-pipeline-ab-identical"),
+                );
+            } else {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 200, 100),
+                    tr_args(
+                        "render-pipeline-ab-changed",
+                        &[
+                            ("count", &diff.changed_count()),
+                            ("sections", &diff.changed_sections().join(", ")),
+                        ],
+                    ),
+                );
+            }
+            ui.checkbox(
+                &mut self.diff_only_changed,
+                tr("render-pipeline-ab-only-changed"),
+            );
+            ui.add_space(5.0);
+
+            let changed_color = egui::Color32::from_rgb(255, 200, 100);
+            egui::Grid::new("pipeline_diff")
+                .num_columns(4)
+                .spacing([12.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong(tr("render-pipeline-ab-section"));
+                    ui.strong(tr("render-pipeline-ab-field"));
+                    ui.strong(tr("render-pipeline-baseline"));
+                    ui.strong(tr("render-pipeline-current"));
+                    ui.end_row();
+
+                    for field in &diff.fields {
+                        let changed = field.is_changed();
+                        if self.diff_only_changed && !changed {
+                            continue;
+                        }
+                        let cell = |text: &str| {
+                            let text = egui::RichText::new(text).monospace();
+                            if changed {
+                                text.color(changed_color).strong()
+                            } else {
+                                text
+                            }
+                        };
+                        ui.label(field.section);
+                        ui.label(cell(&field.field));
+                        ui.label(cell(&field.baseline));
+                        ui.label(cell(&field.current));
+                        ui.end_row();
+                    }
+                });
+        });
     }
 
     // Helper methods for applying tooltips based on enum values
@@ -1252,6 +2039,14 @@ impl RenderPipelinePanel {
         }
     }
 
+    fn polygon_mode_tooltip(response: egui::Response, mode: PolygonMode) -> egui::Response {
+        match mode {
+            PolygonMode::Fill => polygon_mode::FILL.apply(response),
+            PolygonMode::Line => polygon_mode::LINE.apply(response),
+            PolygonMode::Point => polygon_mode::POINT.apply(response),
+        }
+    }
+
     fn compare_function_tooltip(response: egui::Response, func: CompareFunction) -> egui::Response {
         match func {
             CompareFunction::Never => compare_function::NEVER.apply(response),
@@ -1364,6 +2159,40 @@ impl RenderPipelinePanel {
         }
     }
 
+    fn render_polygon_mode_combo(
+        ui: &mut egui::Ui,
+        polygon_mode: &mut PolygonMode,
+        features: wgpu::Features,
+    ) {
+        egui::ComboBox::from_id_salt("polygon_mode")
+            .selected_text(Self::polygon_mode_name(*polygon_mode))
+            .show_ui(ui, |ui| {
+                for mode in POLYGON_MODES {
+                    let button = egui::Button::selectable(
+                        *polygon_mode == mode,
+                        Self::polygon_mode_name(mode),
+                    );
+                    let response = ui
+                        .add_enabled(mode.is_supported(features), button)
+                        .on_disabled_hover_text(tr_args(
+                            "render-pipeline-polygon-mode-feature",
+                            &[("feature", &format!("{:?}", mode.required_features()))],
+                        ));
+                    if response.clicked() {
+                        *polygon_mode = mode;
+                    }
+                }
+            });
+    }
+
+    fn polygon_mode_name(polygon_mode: PolygonMode) -> &'static str {
+        match polygon_mode {
+            PolygonMode::Fill => "Fill",
+            PolygonMode::Line => "Line (wireframe)",
+            PolygonMode::Point => "Point",
+        }
+    }
+
     fn render_depth_format_combo(ui: &mut egui::Ui, format: &mut DepthFormat) {
         egui::ComboBox::from_id_salt("depth_format")
             .selected_text(format.name())
@@ -1554,18 +2383,28 @@ impl PipelinePreset {
         ]
     }
 
-    fn name(&self) -> &'static str {
+    fn name(&self) -> String {
         match self {
-            PipelinePreset::Default => "Default",
-            PipelinePreset::BasicTriangle => "Basic Triangle",
-            PipelinePreset::DepthTested => "Depth Tested",
-            PipelinePreset::AlphaBlended => "Alpha Blended",
-            PipelinePreset::Wireframe => "Wireframe",
-            PipelinePreset::Multisample4x => "4x MSAA",
+            PipelinePreset::Default => tr("render-pipeline-preset-default"),
+            PipelinePreset::BasicTriangle => tr("render-pipeline-preset-basic-triangle"),
+            PipelinePreset::DepthTested => tr("render-pipeline-preset-depth-tested"),
+            PipelinePreset::AlphaBlended => tr("render-pipeline-preset-alpha-blended"),
+            PipelinePreset::Wireframe => tr("render-pipeline-preset-wireframe"),
+            PipelinePreset::Multisample4x => tr("render-pipeline-preset-msaa"),
         }
     }
 }
 
+impl ResourceRecreate for RenderPipelinePanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // Previews rebuild their pipelines on the next frame
+        self.preview_state = None;
+        self.baseline_preview_state = None;
+        self.pipeline_cache.clear();
+        self.gpu_errors.clear_all();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1659,11 +2498,30 @@ mod tests {
         panel.apply_preset(PipelinePreset::Wireframe);
 
         assert_eq!(panel.topology, PrimitiveTopology::LineList);
+        assert_eq!(panel.polygon_mode, PolygonMode::Fill);
         assert_eq!(panel.cull_mode, CullMode::None);
         assert!(panel.enable_depth_stencil);
         assert!(!panel.blend_enabled);
     }
 
+    #[test]
+    fn test_preset_wireframe_uses_line_polygon_mode() {
+        let mut panel = RenderPipelinePanel::new();
+        panel.set_device_features(wgpu::Features::POLYGON_MODE_LINE);
+        panel.apply_preset(PipelinePreset::Wireframe);
+
+        assert_eq!(panel.topology, PrimitiveTopology::TriangleList);
+        assert_eq!(panel.polygon_mode, PolygonMode::Line);
+        assert_eq!(panel.descriptor.primitive().polygon_mode, PolygonMode::Line);
+
+        // Other presets go back to filled triangles
+        panel.apply_preset(PipelinePreset::DepthTested);
+        assert_eq!(panel.polygon_mode, PolygonMode::Fill);
+        // Resetting keeps the device features
+        panel.apply_preset(PipelinePreset::Default);
+        assert_eq!(panel.device_features, wgpu::Features::POLYGON_MODE_LINE);
+    }
+
     #[test]
     fn test_preset_multisample_4x() {
         let mut panel = RenderPipelinePanel::new();
@@ -1835,4 +2693,66 @@ mod tests {
         panel.update_descriptor();
         // The test just verifies that update_descriptor doesn't panic with blending enabled
     }
+
+    #[test]
+    fn test_baseline_diff() {
+        let mut panel = RenderPipelinePanel::new();
+        assert!(panel.baseline_diff().is_none());
+
+        panel.snapshot_baseline();
+        assert!(panel.baseline_diff().unwrap().is_identical());
+
+        panel.cull_mode = CullMode::Back;
+        panel.enable_depth_stencil = true;
+        let diff = panel.baseline_diff().unwrap();
+        assert!(diff
+            .changed()
+            .any(|f| f.field == "cull_mode" && f.current == "Back"));
+        assert_eq!(diff.changed_sections(), vec!["Primitive", "Depth-Stencil"]);
+
+        // Resetting keeps the baseline, which was taken from the defaults
+        panel.apply_preset(PipelinePreset::Default);
+        assert!(panel.baseline().is_some());
+        assert!(panel.baseline_diff().unwrap().is_identical());
+
+        panel.clear_baseline();
+        assert!(panel.baseline().is_none());
+    }
+
+    #[test]
+    fn test_reset_keeps_the_pipeline_cache() {
+        let mut panel = RenderPipelinePanel::new();
+        panel.pipeline_cache.set_capacity(3);
+        panel.apply_preset(PipelinePreset::Default);
+        assert_eq!(panel.pipeline_cache.capacity(), 3);
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let mut panel = RenderPipelinePanel::new();
+        panel.apply_preset(PipelinePreset::AlphaBlended);
+        panel.topology = PrimitiveTopology::LineStrip;
+        panel.polygon_mode = PolygonMode::Point;
+        panel.enable_depth_stencil = true;
+        panel.depth_format = DepthFormat::Depth32Float;
+        panel.stencil_back_pass_op = StencilOperation::IncrementWrap;
+        panel.target_format = TargetFormat::Rgba16Float;
+        panel.write_green = false;
+        panel.update_descriptor();
+
+        let mut restored = RenderPipelinePanel::new();
+        restored.import_state(&panel.export_state());
+        assert_eq!(restored.topology, PrimitiveTopology::LineStrip);
+        assert_eq!(restored.polygon_mode, PolygonMode::Point);
+        assert_eq!(restored.depth_format, DepthFormat::Depth32Float);
+        assert_eq!(
+            restored.stencil_back_pass_op,
+            StencilOperation::IncrementWrap
+        );
+        assert_eq!(restored.target_format, TargetFormat::Rgba16Float);
+        assert_eq!(restored.color_blend_dst, BlendFactor::OneMinusSrcAlpha);
+        assert!(restored.blend_enabled);
+        assert!(!restored.write_green);
+        assert!(pipeline_diff::diff(&panel.descriptor, &restored.descriptor).is_identical());
+    }
 }
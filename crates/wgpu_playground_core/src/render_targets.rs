@@ -0,0 +1,1457 @@
+//! Named off-screen render targets and a small render graph that draws into them
+//!
+//! [`RenderTargetManager`] owns color and depth textures created from
+//! [`RenderTargetDesc`]s and looks them up by name. A [`RenderGraph`] is an
+//! ordered list of [`GraphPass`]es; each pass names the targets it binds as
+//! color, resolve and depth attachments and what it draws: overlapping
+//! triangles that only stack correctly with a depth attachment, or another
+//! target copied across the whole pass. [`RenderGraphExecutor`] records the
+//! passes into one command buffer, and [`read_target`] copies a target back
+//! as floats so it can be inspected texel by texel.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::visual_regression::formats::f16_to_f32;
+
+/// Formats offered for color targets; all of them can be read back
+pub const COLOR_FORMATS: &[wgpu::TextureFormat] = &[
+    wgpu::TextureFormat::Rgba8Unorm,
+    wgpu::TextureFormat::Rgba8UnormSrgb,
+    wgpu::TextureFormat::Bgra8Unorm,
+    wgpu::TextureFormat::Rgba16Float,
+    wgpu::TextureFormat::Rgba32Float,
+    wgpu::TextureFormat::R8Unorm,
+    wgpu::TextureFormat::R32Float,
+];
+
+/// Formats offered for depth targets; all of them can be read back
+pub const DEPTH_FORMATS: &[wgpu::TextureFormat] = &[
+    wgpu::TextureFormat::Depth32Float,
+    wgpu::TextureFormat::Depth16Unorm,
+];
+
+/// Sample counts every WebGPU implementation supports for some formats
+pub const SAMPLE_COUNTS: [u32; 2] = [1, 4];
+
+/// Largest width or height of a target
+pub const MAX_TARGET_SIZE: u32 = 4096;
+
+/// Errors from creating targets, validating a graph or reading a target back
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderTargetError {
+    /// Targets need a name to be bound by
+    EmptyName,
+    /// Another target already has this name
+    DuplicateName(String),
+    InvalidSize {
+        width: u32,
+        height: u32,
+    },
+    UnsupportedFormat(wgpu::TextureFormat),
+    UnsupportedSampleCount {
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    },
+    /// No target has this name
+    UnknownTarget(String),
+    /// A pass's attachments or source can't be used together
+    InvalidPass {
+        pass: String,
+        reason: String,
+    },
+    /// Copying a target back from the GPU failed
+    Readback(String),
+}
+
+impl fmt::Display for RenderTargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderTargetError::EmptyName => write!(f, "Render targets need a name"),
+            RenderTargetError::DuplicateName(name) => {
+                write!(f, "A render target named '{}' already exists", name)
+            }
+            RenderTargetError::InvalidSize { width, height } => write!(
+                f,
+                "Invalid size {}x{}: width and height must be between 1 and {}",
+                width, height, MAX_TARGET_SIZE
+            ),
+            RenderTargetError::UnsupportedFormat(format) => {
+                write!(f, "{:?} can't be used as a render target here", format)
+            }
+            RenderTargetError::UnsupportedSampleCount {
+                format,
+                sample_count,
+            } => write!(
+                f,
+                "{:?} does not support {}x multisampling",
+                format, sample_count
+            ),
+            RenderTargetError::UnknownTarget(name) => {
+                write!(f, "No render target named '{}'", name)
+            }
+            RenderTargetError::InvalidPass { pass, reason } => {
+                write!(f, "Pass '{}': {}", pass, reason)
+            }
+            RenderTargetError::Readback(msg) => write!(f, "Render target readback failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RenderTargetError {}
+
+/// Description of an off-screen render target
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderTargetDesc {
+    pub name: String,
+    pub format: wgpu::TextureFormat,
+    pub width: u32,
+    pub height: u32,
+    pub sample_count: u32,
+}
+
+impl RenderTargetDesc {
+    /// A single-sampled target
+    pub fn new(
+        name: impl Into<String>,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            format,
+            width,
+            height,
+            sample_count: 1,
+        }
+    }
+
+    pub fn with_sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count;
+        self
+    }
+
+    /// Whether the target is bound as a depth attachment
+    pub fn is_depth(&self) -> bool {
+        self.format.is_depth_stencil_format()
+    }
+
+    /// Check the description against the formats this module offers and
+    /// the sample counts the format is guaranteed to support with `features`
+    pub fn validate(&self, features: wgpu::Features) -> Result<(), RenderTargetError> {
+        if self.name.trim().is_empty() {
+            return Err(RenderTargetError::EmptyName);
+        }
+        if !(1..=MAX_TARGET_SIZE).contains(&self.width)
+            || !(1..=MAX_TARGET_SIZE).contains(&self.height)
+        {
+            return Err(RenderTargetError::InvalidSize {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        if !COLOR_FORMATS.contains(&self.format) && !DEPTH_FORMATS.contains(&self.format) {
+            return Err(RenderTargetError::UnsupportedFormat(self.format));
+        }
+        let flags = self.format.guaranteed_format_features(features).flags;
+        if !flags.sample_count_supported(self.sample_count) {
+            return Err(RenderTargetError::UnsupportedSampleCount {
+                format: self.format,
+                sample_count: self.sample_count,
+            });
+        }
+        Ok(())
+    }
+
+    /// Multisampled targets can't be sampled by a pass or copied, so they
+    /// are only attachments
+    fn usage(&self) -> wgpu::TextureUsages {
+        if self.sample_count == 1 {
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC
+        } else {
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+        }
+    }
+}
+
+/// A created render target
+pub struct RenderTarget {
+    desc: RenderTargetDesc,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+impl RenderTarget {
+    fn new(device: &wgpu::Device, desc: RenderTargetDesc) -> Self {
+        ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&desc.name),
+            size: wgpu::Extent3d {
+                width: desc.width,
+                height: desc.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: desc.sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: desc.format,
+            usage: desc.usage(),
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            desc,
+            texture,
+            view,
+        }
+    }
+
+    pub fn desc(&self) -> &RenderTargetDesc {
+        &self.desc
+    }
+
+    pub fn name(&self) -> &str {
+        &self.desc.name
+    }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+}
+
+/// Named render targets, in creation order
+#[derive(Default)]
+pub struct RenderTargetManager {
+    targets: Vec<RenderTarget>,
+}
+
+impl RenderTargetManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate `desc` and create its texture
+    pub fn create(
+        &mut self,
+        device: &wgpu::Device,
+        desc: RenderTargetDesc,
+    ) -> Result<&RenderTarget, RenderTargetError> {
+        desc.validate(device.features())?;
+        if self.get(&desc.name).is_some() {
+            return Err(RenderTargetError::DuplicateName(desc.name));
+        }
+        self.targets.push(RenderTarget::new(device, desc));
+        Ok(self.targets.last().expect("target was just pushed"))
+    }
+
+    /// Remove the target named `name`, returning whether it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        let count = self.targets.len();
+        self.targets.retain(|target| target.name() != name);
+        self.targets.len() != count
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RenderTarget> {
+        self.targets.iter().find(|target| target.name() == name)
+    }
+
+    pub fn targets(&self) -> &[RenderTarget] {
+        &self.targets
+    }
+
+    pub fn descs(&self) -> Vec<RenderTargetDesc> {
+        self.targets
+            .iter()
+            .map(|target| target.desc.clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Create every target again on a new device
+    pub fn recreate(&mut self, device: &wgpu::Device) {
+        for target in &mut self.targets {
+            *target = RenderTarget::new(device, target.desc.clone());
+        }
+    }
+}
+
+/// What a pass draws into its attachments
+#[derive(Debug, Clone, PartialEq)]
+pub enum PassContent {
+    /// Only clear the attachments
+    Clear,
+    /// Three overlapping triangles; the one drawn first is nearest, so they
+    /// only stack front to back with a depth attachment
+    Shapes,
+    /// Copy the named color target across the whole pass, texel by texel
+    Sample(String),
+}
+
+impl PassContent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PassContent::Clear => "Clear",
+            PassContent::Shapes => "Shapes",
+            PassContent::Sample(_) => "Sample target",
+        }
+    }
+}
+
+/// One render pass of a [`RenderGraph`], with its attachments given by
+/// target name
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphPass {
+    pub name: String,
+    pub color: Option<String>,
+    /// Single-sampled target the multisampled color attachment resolves into
+    pub resolve: Option<String>,
+    pub depth: Option<String>,
+    pub content: PassContent,
+    pub clear_color: [f32; 4],
+    /// Load the attachments' previous contents instead of clearing them
+    pub keep_contents: bool,
+}
+
+impl GraphPass {
+    /// A pass drawing [`PassContent::Shapes`] with no attachments yet
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            color: None,
+            resolve: None,
+            depth: None,
+            content: PassContent::Shapes,
+            clear_color: [0.05, 0.05, 0.08, 1.0],
+            keep_contents: false,
+        }
+    }
+
+    /// Names of the targets bound as attachments
+    pub fn attachments(&self) -> impl Iterator<Item = &str> {
+        [&self.color, &self.resolve, &self.depth]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    fn invalid(&self, reason: impl Into<String>) -> RenderTargetError {
+        RenderTargetError::InvalidPass {
+            pass: self.name.clone(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Check the pass against the targets it names
+    pub fn validate(
+        &self,
+        targets: &[RenderTargetDesc],
+        features: wgpu::Features,
+    ) -> Result<(), RenderTargetError> {
+        let lookup = |name: &str| {
+            targets
+                .iter()
+                .find(|desc| desc.name == name)
+                .ok_or_else(|| RenderTargetError::UnknownTarget(name.to_string()))
+        };
+        let color = self.color.as_deref().map(lookup).transpose()?;
+        let resolve = self.resolve.as_deref().map(lookup).transpose()?;
+        let depth = self.depth.as_deref().map(lookup).transpose()?;
+
+        if color.is_none() && depth.is_none() {
+            return Err(self.invalid("needs a color or depth attachment"));
+        }
+        if color.is_some_and(RenderTargetDesc::is_depth) {
+            return Err(self.invalid("the color attachment is a depth target"));
+        }
+        if depth.is_some_and(|desc| !desc.is_depth()) {
+            return Err(self.invalid("the depth attachment is a color target"));
+        }
+        let attached: Vec<&RenderTargetDesc> =
+            [color, resolve, depth].into_iter().flatten().collect();
+        if attached
+            .iter()
+            .any(|desc| (desc.width, desc.height) != (attached[0].width, attached[0].height))
+        {
+            return Err(self.invalid("all attachments must have the same size"));
+        }
+        if let (Some(color), Some(depth)) = (color, depth) {
+            if color.sample_count != depth.sample_count {
+                return Err(
+                    self.invalid("the color and depth attachments must have the same sample count")
+                );
+            }
+        }
+
+        if let Some(resolve) = resolve {
+            let Some(color) = color else {
+                return Err(self.invalid("a resolve target needs a color attachment"));
+            };
+            if color.sample_count == 1 {
+                return Err(self.invalid("only a multisampled color attachment can be resolved"));
+            }
+            if resolve.sample_count != 1 {
+                return Err(self.invalid("the resolve target must be single-sampled"));
+            }
+            if resolve.format != color.format {
+                return Err(
+                    self.invalid("the resolve target must have the color attachment's format")
+                );
+            }
+            let flags = color.format.guaranteed_format_features(features).flags;
+            if !flags.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE) {
+                return Err(self.invalid(format!("{:?} can't be resolved", color.format)));
+            }
+        }
+
+        if let PassContent::Sample(source) = &self.content {
+            let source_desc = lookup(source)?;
+            if color.is_none() {
+                return Err(self.invalid("sampling a target needs a color attachment to draw into"));
+            }
+            if source_desc.is_depth() {
+                return Err(self.invalid("depth targets can't be sampled"));
+            }
+            if source_desc.sample_count != 1 {
+                return Err(self.invalid(
+                    "multisampled targets can't be sampled; sample their resolve target",
+                ));
+            }
+            if self.attachments().any(|name| name == source) {
+                return Err(self.invalid("a pass can't sample a target it renders to"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Passes recorded in order into one command buffer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderGraph {
+    pub passes: Vec<GraphPass>,
+}
+
+impl RenderGraph {
+    /// Validate every pass, stopping at the first error
+    pub fn validate(
+        &self,
+        targets: &[RenderTargetDesc],
+        features: wgpu::Features,
+    ) -> Result<(), RenderTargetError> {
+        self.passes
+            .iter()
+            .try_for_each(|pass| pass.validate(targets, features))
+    }
+
+    /// Remove `name` from every pass that binds or samples it
+    pub fn forget_target(&mut self, name: &str) {
+        for pass in &mut self.passes {
+            for slot in [&mut pass.color, &mut pass.resolve, &mut pass.depth] {
+                if slot.as_deref() == Some(name) {
+                    *slot = None;
+                }
+            }
+            if pass.content == PassContent::Sample(name.to_string()) {
+                pass.content = PassContent::Clear;
+            }
+        }
+    }
+
+    /// Targets and passes of a small example graph: a 4x multisampled scene
+    /// with depth, resolved into a single-sampled target that a second pass
+    /// copies into a half-float target
+    pub fn example() -> (Vec<RenderTargetDesc>, Self) {
+        let targets = vec![
+            RenderTargetDesc::new("scene_msaa", wgpu::TextureFormat::Rgba8Unorm, 256, 256)
+                .with_sample_count(4),
+            RenderTargetDesc::new("scene_depth", wgpu::TextureFormat::Depth32Float, 256, 256)
+                .with_sample_count(4),
+            RenderTargetDesc::new("scene", wgpu::TextureFormat::Rgba8Unorm, 256, 256),
+            RenderTargetDesc::new("post", wgpu::TextureFormat::Rgba16Float, 256, 256),
+        ];
+        let scene = GraphPass {
+            color: Some("scene_msaa".to_string()),
+            resolve: Some("scene".to_string()),
+            depth: Some("scene_depth".to_string()),
+            ..GraphPass::new("Scene")
+        };
+        let post = GraphPass {
+            color: Some("post".to_string()),
+            content: PassContent::Sample("scene".to_string()),
+            ..GraphPass::new("Post")
+        };
+        (
+            targets,
+            Self {
+                passes: vec![scene, post],
+            },
+        )
+    }
+}
+
+pub const RENDER_GRAPH_SHADER: &str = r#"
+struct ShapeOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+}
+
+// Three triangles, nearest first, each shifted right and up from the last
+@vertex
+fn vs_shapes(@builtin(vertex_index) index: u32) -> ShapeOut {
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-0.48, -0.4),
+        vec2<f32>(0.48, -0.4),
+        vec2<f32>(0.0, 0.48),
+    );
+    var colors = array<vec3<f32>, 3>(
+        vec3<f32>(0.9, 0.2, 0.2),
+        vec3<f32>(0.2, 0.8, 0.3),
+        vec3<f32>(0.2, 0.4, 0.9),
+    );
+    let shape = index / 3u;
+    let offset = (f32(shape) - 1.0) * vec2<f32>(0.2, 0.1);
+    let depth = 0.25 + 0.25 * f32(shape);
+    var out: ShapeOut;
+    out.position = vec4<f32>(corners[index % 3u] + offset, depth, 1.0);
+    out.color = colors[shape];
+    return out;
+}
+
+@fragment
+fn fs_shapes(in: ShapeOut) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+
+struct SampleOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) index: u32) -> SampleOut {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: SampleOut;
+    out.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+// textureLoad instead of a sampler, so unfilterable float targets work too
+@fragment
+fn fs_sample(in: SampleOut) -> @location(0) vec4<f32> {
+    let size = textureDimensions(source);
+    let texel = min(vec2<u32>(in.uv * vec2<f32>(size)), size - 1u);
+    return textureLoad(source, texel, 0);
+}
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PipelineKey {
+    sample_source: bool,
+    color: Option<wgpu::TextureFormat>,
+    depth: Option<wgpu::TextureFormat>,
+    sample_count: u32,
+}
+
+/// Records a [`RenderGraph`] with pipelines created for each combination
+/// of attachment formats it meets
+pub struct RenderGraphExecutor {
+    shader: wgpu::ShaderModule,
+    shapes_layout: wgpu::PipelineLayout,
+    sample_layout: wgpu::PipelineLayout,
+    source_layout: wgpu::BindGroupLayout,
+    pipelines: HashMap<PipelineKey, wgpu::RenderPipeline>,
+}
+
+impl RenderGraphExecutor {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let tracker = ApiCoverageTracker::global();
+
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Render Graph Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_GRAPH_SHADER.into()),
+        });
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let source_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Render Graph Source Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            }],
+        });
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let shapes_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Render Graph Shapes Layout"),
+            bind_group_layouts: &[],
+            immediate_size: 0,
+        });
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let sample_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Render Graph Sample Layout"),
+            bind_group_layouts: &[Some(&source_layout)],
+            immediate_size: 0,
+        });
+
+        Self {
+            shader,
+            shapes_layout,
+            sample_layout,
+            source_layout,
+            pipelines: HashMap::new(),
+        }
+    }
+
+    fn pipeline(&mut self, device: &wgpu::Device, key: PipelineKey) -> &wgpu::RenderPipeline {
+        let Self {
+            shader,
+            shapes_layout,
+            sample_layout,
+            pipelines,
+            ..
+        } = self;
+        pipelines.entry(key).or_insert_with(|| {
+            let (layout, vertex, fragment) = if key.sample_source {
+                (&*sample_layout, "vs_fullscreen", "fs_sample")
+            } else {
+                (&*shapes_layout, "vs_shapes", "fs_shapes")
+            };
+            let targets = [key.color.map(|format| wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })];
+            ApiCoverageTracker::global()
+                .record(ApiCategory::RenderPipeline, "create_render_pipeline");
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Render Graph Pipeline"),
+                layout: Some(layout),
+                vertex: wgpu::VertexState {
+                    module: shader,
+                    entry_point: Some(vertex),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: key.color.map(|_| wgpu::FragmentState {
+                    module: shader,
+                    entry_point: Some(fragment),
+                    targets: &targets,
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: key.depth.map(|format| wgpu::DepthStencilState {
+                    format,
+                    depth_write_enabled: Some(true),
+                    depth_compare: Some(wgpu::CompareFunction::Less),
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState {
+                    count: key.sample_count,
+                    ..Default::default()
+                },
+                multiview_mask: None,
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        })
+    }
+
+    /// Validate `graph` and submit all of its passes
+    pub fn execute(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        graph: &RenderGraph,
+        targets: &RenderTargetManager,
+    ) -> Result<(), RenderTargetError> {
+        graph.validate(&targets.descs(), device.features())?;
+        let tracker = ApiCoverageTracker::global();
+        let target = |name: &Option<String>| {
+            name.as_deref()
+                .map(|name| targets.get(name).expect("graph was validated"))
+        };
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Render Graph Encoder"),
+        });
+        for pass in &graph.passes {
+            let color = target(&pass.color);
+            let resolve = target(&pass.resolve);
+            let depth = target(&pass.depth);
+            let sample_count = color.or(depth).map_or(1, |t| t.desc.sample_count);
+
+            let draw = match &pass.content {
+                PassContent::Clear => None,
+                PassContent::Shapes => Some((None, 9)),
+                PassContent::Sample(source) => {
+                    let source = targets.get(source).expect("graph was validated");
+                    tracker.record(ApiCategory::BindGroup, "create_bind_group");
+                    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: Some("Render Graph Source Bind Group"),
+                        layout: &self.source_layout,
+                        entries: &[wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(source.view()),
+                        }],
+                    });
+                    Some((Some(bind_group), 3))
+                }
+            };
+            let pipeline = draw.as_ref().map(|(bind_group, _)| {
+                self.pipeline(
+                    device,
+                    PipelineKey {
+                        sample_source: bind_group.is_some(),
+                        color: color.map(|t| t.desc.format),
+                        depth: depth.map(|t| t.desc.format),
+                        sample_count,
+                    },
+                )
+            });
+
+            let [r, g, b, a] = pass.clear_color.map(f64::from);
+            let color_load = if pass.keep_contents {
+                wgpu::LoadOp::Load
+            } else {
+                wgpu::LoadOp::Clear(wgpu::Color { r, g, b, a })
+            };
+            let depth_load = if pass.keep_contents {
+                wgpu::LoadOp::Load
+            } else {
+                wgpu::LoadOp::Clear(1.0)
+            };
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&pass.name),
+                color_attachments: &[color.map(|color| wgpu::RenderPassColorAttachment {
+                    view: color.view(),
+                    resolve_target: resolve.map(RenderTarget::view),
+                    ops: wgpu::Operations {
+                        load: color_load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: depth.map(|depth| {
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view: depth.view(),
+                        depth_ops: Some(wgpu::Operations {
+                            load: depth_load,
+                            store: wgpu::StoreOp::Store,
+                        }),
+                        stencil_ops: None,
+                    }
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            if let (Some(pipeline), Some((bind_group, vertex_count))) = (pipeline, &draw) {
+                tracker.record(ApiCategory::RenderPass, "set_pipeline");
+                render_pass.set_pipeline(pipeline);
+                if let Some(bind_group) = bind_group {
+                    tracker.record(ApiCategory::RenderPass, "set_bind_group");
+                    render_pass.set_bind_group(0, bind_group, &[]);
+                }
+                tracker.record(ApiCategory::RenderPass, "draw");
+                render_pass.draw(0..*vertex_count, 0..1);
+            }
+        }
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+}
+
+/// Channels the inspector can isolate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    All,
+    R,
+    G,
+    B,
+    A,
+}
+
+impl Channel {
+    pub const ALL: [Channel; 5] = [Channel::All, Channel::R, Channel::G, Channel::B, Channel::A];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Channel::All => "RGB",
+            Channel::R => "R",
+            Channel::G => "G",
+            Channel::B => "B",
+            Channel::A => "A",
+        }
+    }
+
+    fn index(self) -> Option<usize> {
+        match self {
+            Channel::All => None,
+            Channel::R => Some(0),
+            Channel::G => Some(1),
+            Channel::B => Some(2),
+            Channel::A => Some(3),
+        }
+    }
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    let c = value as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn unorm8(texel: &[u8]) -> [f32; 4] {
+    std::array::from_fn(|i| texel[i] as f32 / 255.0)
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// A target copied back to the CPU, as the values a shader reading it
+/// would see: sRGB formats are decoded to linear, and channels the format
+/// lacks read as 0, or 1 for alpha
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetReadback {
+    pub format: wgpu::TextureFormat,
+    pub width: u32,
+    pub height: u32,
+    texels: Vec<[f32; 4]>,
+}
+
+impl TargetReadback {
+    /// Decode tightly packed texel data of `format`
+    pub fn decode(
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<Self, RenderTargetError> {
+        let texel_size = format
+            .block_copy_size(None)
+            .filter(|_| COLOR_FORMATS.contains(&format) || DEPTH_FORMATS.contains(&format))
+            .ok_or(RenderTargetError::UnsupportedFormat(format))? as usize;
+        let count = (width * height) as usize;
+        if data.len() < count * texel_size {
+            return Err(RenderTargetError::Readback(format!(
+                "expected {} bytes of texel data, got {}",
+                count * texel_size,
+                data.len()
+            )));
+        }
+        let f32_at = |t: &[u8], i: usize| f32::from_le_bytes([t[i], t[i + 1], t[i + 2], t[i + 3]]);
+        let texels = data[..count * texel_size]
+            .chunks_exact(texel_size)
+            .map(|t| match format {
+                wgpu::TextureFormat::Rgba8Unorm => unorm8(t),
+                wgpu::TextureFormat::Rgba8UnormSrgb => [
+                    srgb_to_linear(t[0]),
+                    srgb_to_linear(t[1]),
+                    srgb_to_linear(t[2]),
+                    t[3] as f32 / 255.0,
+                ],
+                wgpu::TextureFormat::Bgra8Unorm => {
+                    let [b, g, r, a] = unorm8(t);
+                    [r, g, b, a]
+                }
+                wgpu::TextureFormat::Rgba16Float => std::array::from_fn(|i| {
+                    f16_to_f32(u16::from_le_bytes([t[i * 2], t[i * 2 + 1]]))
+                }),
+                wgpu::TextureFormat::Rgba32Float => std::array::from_fn(|i| f32_at(t, i * 4)),
+                wgpu::TextureFormat::R8Unorm => [t[0] as f32 / 255.0, 0.0, 0.0, 1.0],
+                wgpu::TextureFormat::Depth16Unorm => [
+                    u16::from_le_bytes([t[0], t[1]]) as f32 / u16::MAX as f32,
+                    0.0,
+                    0.0,
+                    1.0,
+                ],
+                // R32Float and Depth32Float
+                _ => [f32_at(t, 0), 0.0, 0.0, 1.0],
+            })
+            .collect();
+        Ok(Self {
+            format,
+            width,
+            height,
+            texels,
+        })
+    }
+
+    /// Number of channels the format stores
+    pub fn channel_count(&self) -> u32 {
+        self.format.components() as u32
+    }
+
+    pub fn texel(&self, x: u32, y: u32) -> Option<[f32; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.texels.get((y * self.width + x) as usize).copied()
+    }
+
+    /// Smallest and largest finite value of `channel`, or of the stored
+    /// color channels for [`Channel::All`]
+    pub fn value_range(&self, channel: Channel) -> Option<(f32, f32)> {
+        let channels = match channel.index() {
+            Some(index) => index..index + 1,
+            None => 0..self.channel_count().min(3) as usize,
+        };
+        self.texels
+            .iter()
+            .flat_map(|texel| texel[channels.clone()].iter().copied())
+            .filter(|value| value.is_finite())
+            .fold(None, |range, value| match range {
+                None => Some((value, value)),
+                Some((min, max)) => Some((min.min(value), max.max(value))),
+            })
+    }
+
+    /// Tightly packed sRGB RGBA8 pixels showing `channel`, with `min..=max`
+    /// mapped to black..white
+    ///
+    /// An isolated channel is shown as grayscale, and single-channel formats
+    /// are always gray. Alpha is ignored unless isolated. For sRGB formats
+    /// the values are encoded again, so an unmodified range shows the
+    /// texels as they were written.
+    pub fn to_rgba8(&self, channel: Channel, min: f32, max: f32) -> Vec<u8> {
+        let span = max - min;
+        let srgb = self.format.is_srgb();
+        let to_byte = |value: f32, encode: bool| {
+            let t = if span.abs() <= f32::EPSILON {
+                if value >= max {
+                    1.0
+                } else {
+                    0.0
+                }
+            } else {
+                ((value - min) / span).clamp(0.0, 1.0)
+            };
+            let t = if encode { linear_to_srgb(t) } else { t };
+            (t * 255.0).round() as u8
+        };
+        let gray = self.channel_count() == 1;
+        self.texels
+            .iter()
+            .flat_map(|texel| match channel.index() {
+                None if !gray => {
+                    let [r, g, b, _] = texel.map(|value| to_byte(value, srgb));
+                    [r, g, b, 255]
+                }
+                None => {
+                    let value = to_byte(texel[0], false);
+                    [value, value, value, 255]
+                }
+                Some(index) => {
+                    let value = to_byte(texel[index], srgb && index < 3);
+                    [value, value, value, 255]
+                }
+            })
+            .collect()
+    }
+}
+
+pub const DEPTH_TO_FLOAT_SHADER: &str = r#"
+// Depth formats can be bound as unfilterable floats, which unlike
+// texture_depth_2d can be loaded from on every backend
+@group(0) @binding(0) var depth: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) u32 {
+    return bitcast<u32>(textureLoad(depth, vec2<u32>(position.xy), 0).r);
+}
+"#;
+
+/// Record a pass writing the depth of `target` as raw `f32` bits into a new
+/// R32Uint texture, which unlike R32Float is renderable on GL
+fn depth_to_float(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    target: &RenderTarget,
+) -> wgpu::Texture {
+    let tracker = ApiCoverageTracker::global();
+    let desc = target.desc();
+
+    tracker.record(ApiCategory::Shader, "create_shader_module");
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Depth To Float Shader"),
+        source: wgpu::ShaderSource::Wgsl(DEPTH_TO_FLOAT_SHADER.into()),
+    });
+    tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Depth To Float Bind Group Layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        }],
+    });
+    tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Depth To Float Pipeline Layout"),
+        bind_group_layouts: &[Some(&bind_group_layout)],
+        immediate_size: 0,
+    });
+    tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Depth To Float Pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::TextureFormat::R32Uint.into())],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+    });
+    tracker.record(ApiCategory::BindGroup, "create_bind_group");
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Depth To Float Bind Group"),
+        layout: &bind_group_layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(target.view()),
+        }],
+    });
+    tracker.record(ApiCategory::Texture, "create_texture");
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth To Float Texture"),
+        size: wgpu::Extent3d {
+            width: desc.width,
+            height: desc.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R32Uint,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+    let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Depth To Float Pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    });
+    tracker.record(ApiCategory::RenderPass, "set_pipeline");
+    pass.set_pipeline(&pipeline);
+    tracker.record(ApiCategory::RenderPass, "set_bind_group");
+    pass.set_bind_group(0, &bind_group, &[]);
+    tracker.record(ApiCategory::RenderPass, "draw");
+    pass.draw(0..3, 0..1);
+    drop(pass);
+    texture
+}
+
+/// Copy a single-sampled target back to the CPU
+///
+/// Blocks until the GPU has finished.
+pub fn read_target(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    target: &RenderTarget,
+) -> Result<TargetReadback, RenderTargetError> {
+    let desc = target.desc();
+    if desc.sample_count != 1 {
+        return Err(RenderTargetError::Readback(
+            "multisampled targets can't be copied; inspect the target they resolve into"
+                .to_string(),
+        ));
+    }
+    let tracker = ApiCoverageTracker::global();
+
+    tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Render Target Readback Encoder"),
+    });
+    // GL can't copy depth textures to buffers, so depth is drawn into a
+    // color texture and copied from there
+    let converted = desc
+        .is_depth()
+        .then(|| depth_to_float(device, &mut encoder, target));
+    let (texture, copy_format) = match &converted {
+        // The bits of R32Uint texels are the depth as R32Float
+        Some(texture) => (texture, wgpu::TextureFormat::R32Float),
+        None => (target.texture(), desc.format),
+    };
+
+    let texel_size = copy_format
+        .block_copy_size(None)
+        .ok_or(RenderTargetError::UnsupportedFormat(desc.format))?;
+    let unpadded_bytes_per_row = desc.width * texel_size;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+    tracker.record(ApiCategory::Buffer, "create_buffer");
+    let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Render Target Readback Buffer"),
+        size: (padded_bytes_per_row * desc.height) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    tracker.record(ApiCategory::CommandEncoder, "copy_texture_to_buffer");
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &staging_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(desc.height),
+            },
+        },
+        wgpu::Extent3d {
+            width: desc.width,
+            height: desc.height,
+            depth_or_array_layers: 1,
+        },
+    );
+    tracker.record(ApiCategory::Queue, "submit");
+    queue.submit(Some(encoder.finish()));
+
+    let buffer_slice = staging_buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    let _ = device.poll(wgpu::PollType::Wait {
+        submission_index: None,
+        timeout: None,
+    });
+    match receiver.recv() {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return Err(RenderTargetError::Readback(e.to_string())),
+        Err(e) => return Err(RenderTargetError::Readback(e.to_string())),
+    }
+
+    let data = buffer_slice.get_mapped_range();
+    let mut texel_data = Vec::with_capacity((unpadded_bytes_per_row * desc.height) as usize);
+    for row in data.chunks(padded_bytes_per_row as usize) {
+        texel_data.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+    }
+    drop(data);
+    staging_buffer.unmap();
+
+    let mut readback = TargetReadback::decode(copy_format, desc.width, desc.height, &texel_data)?;
+    readback.format = desc.format;
+    Ok(readback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_targets() -> Vec<RenderTargetDesc> {
+        RenderGraph::example().0
+    }
+
+    fn pass_error(pass: &GraphPass) -> String {
+        match pass.validate(&example_targets(), wgpu::Features::empty()) {
+            Err(RenderTargetError::InvalidPass { reason, .. }) => reason,
+            other => panic!("expected an invalid pass, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        for source in [RENDER_GRAPH_SHADER, DEPTH_TO_FLOAT_SHADER] {
+            let module = naga::front::wgsl::parse_str(source).expect("parses");
+            naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::empty(),
+            )
+            .validate(&module)
+            .expect("validates");
+        }
+    }
+
+    #[test]
+    fn test_desc_validation() {
+        let features = wgpu::Features::empty();
+        let desc = RenderTargetDesc::new("color", wgpu::TextureFormat::Rgba8Unorm, 64, 64);
+        assert_eq!(desc.validate(features), Ok(()));
+        assert_eq!(desc.clone().with_sample_count(4).validate(features), Ok(()));
+        assert_eq!(
+            RenderTargetDesc::new(" ", wgpu::TextureFormat::Rgba8Unorm, 64, 64).validate(features),
+            Err(RenderTargetError::EmptyName)
+        );
+        assert_eq!(
+            RenderTargetDesc::new("big", wgpu::TextureFormat::Rgba8Unorm, 0, 64).validate(features),
+            Err(RenderTargetError::InvalidSize {
+                width: 0,
+                height: 64
+            })
+        );
+        assert_eq!(
+            RenderTargetDesc::new("hdr", wgpu::TextureFormat::Rgba32Float, 64, 64)
+                .with_sample_count(4)
+                .validate(features),
+            Err(RenderTargetError::UnsupportedSampleCount {
+                format: wgpu::TextureFormat::Rgba32Float,
+                sample_count: 4
+            })
+        );
+        assert_eq!(
+            RenderTargetDesc::new("stencil", wgpu::TextureFormat::Stencil8, 64, 64)
+                .validate(features),
+            Err(RenderTargetError::UnsupportedFormat(
+                wgpu::TextureFormat::Stencil8
+            ))
+        );
+        assert!(RenderTargetDesc::new("d", wgpu::TextureFormat::Depth16Unorm, 1, 1).is_depth());
+    }
+
+    #[test]
+    fn test_example_graph_is_valid() {
+        let (targets, graph) = RenderGraph::example();
+        for desc in &targets {
+            assert_eq!(desc.validate(wgpu::Features::empty()), Ok(()));
+        }
+        assert_eq!(graph.validate(&targets, wgpu::Features::empty()), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_passes() {
+        let pass = GraphPass::new("Empty");
+        assert_eq!(pass_error(&pass), "needs a color or depth attachment");
+
+        let pass = GraphPass {
+            color: Some("missing".to_string()),
+            ..GraphPass::new("Missing")
+        };
+        assert_eq!(
+            pass.validate(&example_targets(), wgpu::Features::empty()),
+            Err(RenderTargetError::UnknownTarget("missing".to_string()))
+        );
+
+        let pass = GraphPass {
+            color: Some("scene_depth".to_string()),
+            ..GraphPass::new("Swapped")
+        };
+        assert_eq!(pass_error(&pass), "the color attachment is a depth target");
+
+        let pass = GraphPass {
+            color: Some("scene".to_string()),
+            depth: Some("scene_depth".to_string()),
+            ..GraphPass::new("Mixed")
+        };
+        assert_eq!(
+            pass_error(&pass),
+            "the color and depth attachments must have the same sample count"
+        );
+
+        let pass = GraphPass {
+            color: Some("scene".to_string()),
+            resolve: Some("post".to_string()),
+            ..GraphPass::new("Resolve")
+        };
+        assert_eq!(
+            pass_error(&pass),
+            "only a multisampled color attachment can be resolved"
+        );
+
+        let pass = GraphPass {
+            color: Some("scene".to_string()),
+            content: PassContent::Sample("scene".to_string()),
+            ..GraphPass::new("Feedback")
+        };
+        assert_eq!(
+            pass_error(&pass),
+            "a pass can't sample a target it renders to"
+        );
+
+        let pass = GraphPass {
+            color: Some("scene".to_string()),
+            content: PassContent::Sample("scene_msaa".to_string()),
+            ..GraphPass::new("Unresolved")
+        };
+        assert_eq!(
+            pass_error(&pass),
+            "multisampled targets can't be sampled; sample their resolve target"
+        );
+
+        let mut targets = example_targets();
+        targets.push(RenderTargetDesc::new(
+            "small",
+            wgpu::TextureFormat::Rgba8Unorm,
+            32,
+            32,
+        ));
+        let pass = GraphPass {
+            color: Some("small".to_string()),
+            depth: Some("scene_depth".to_string()),
+            ..GraphPass::new("Sizes")
+        };
+        assert!(matches!(
+            pass.validate(&targets, wgpu::Features::empty()),
+            Err(RenderTargetError::InvalidPass { reason, .. })
+                if reason == "all attachments must have the same size"
+        ));
+    }
+
+    #[test]
+    fn test_forget_target() {
+        let (_, mut graph) = RenderGraph::example();
+        graph.forget_target("scene");
+        assert_eq!(graph.passes[0].resolve, None);
+        assert_eq!(graph.passes[0].color.as_deref(), Some("scene_msaa"));
+        assert_eq!(graph.passes[1].content, PassContent::Clear);
+    }
+
+    #[test]
+    fn test_decode_formats() {
+        let readback =
+            TargetReadback::decode(wgpu::TextureFormat::Bgra8Unorm, 1, 1, &[0, 51, 255, 255])
+                .unwrap();
+        assert_eq!(readback.texel(0, 0), Some([1.0, 0.2, 0.0, 1.0]));
+        assert_eq!(readback.texel(1, 0), None);
+
+        let srgb = TargetReadback::decode(
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            1,
+            1,
+            &[188, 0, 255, 128],
+        )
+        .unwrap();
+        let [r, g, b, a] = srgb.texel(0, 0).unwrap();
+        assert!((r - 0.5).abs() < 0.005);
+        assert_eq!((g, b), (0.0, 1.0));
+        assert!((a - 128.0 / 255.0).abs() < 1e-6);
+
+        // 1.0 and -2.0 as half floats
+        let half = [0x00, 0x3c, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x3c];
+        let readback =
+            TargetReadback::decode(wgpu::TextureFormat::Rgba16Float, 1, 1, &half).unwrap();
+        assert_eq!(readback.texel(0, 0), Some([1.0, -2.0, 0.0, 1.0]));
+
+        let depth = TargetReadback::decode(
+            wgpu::TextureFormat::Depth32Float,
+            1,
+            1,
+            &0.25f32.to_le_bytes(),
+        )
+        .unwrap();
+        assert_eq!(depth.texel(0, 0), Some([0.25, 0.0, 0.0, 1.0]));
+        assert_eq!(depth.channel_count(), 1);
+
+        assert!(matches!(
+            TargetReadback::decode(wgpu::TextureFormat::Rgba8Unorm, 2, 2, &[0; 4]),
+            Err(RenderTargetError::Readback(_))
+        ));
+    }
+
+    #[test]
+    fn test_channel_isolation() {
+        let data = [255, 128, 0, 64, 0, 0, 0, 255];
+        let readback =
+            TargetReadback::decode(wgpu::TextureFormat::Rgba8Unorm, 2, 1, &data).unwrap();
+        assert_eq!(
+            readback.to_rgba8(Channel::All, 0.0, 1.0),
+            vec![255, 128, 0, 255, 0, 0, 0, 255]
+        );
+        assert_eq!(
+            readback.to_rgba8(Channel::G, 0.0, 1.0),
+            vec![128, 128, 128, 255, 0, 0, 0, 255]
+        );
+        assert_eq!(
+            readback.to_rgba8(Channel::A, 0.0, 1.0),
+            vec![64, 64, 64, 255, 255, 255, 255, 255]
+        );
+        // Narrowing the range stretches the values
+        assert_eq!(
+            readback.to_rgba8(Channel::R, 0.0, 0.5)[..4],
+            [255, 255, 255, 255]
+        );
+        assert_eq!(readback.value_range(Channel::A), Some((64.0 / 255.0, 1.0)));
+        assert_eq!(readback.value_range(Channel::All), Some((0.0, 1.0)));
+
+        // sRGB targets show the bytes that were stored
+        let srgb =
+            TargetReadback::decode(wgpu::TextureFormat::Rgba8UnormSrgb, 2, 1, &data).unwrap();
+        assert_eq!(
+            srgb.to_rgba8(Channel::All, 0.0, 1.0),
+            readback.to_rgba8(Channel::All, 0.0, 1.0)
+        );
+
+        // Depth is gray in every view
+        let depth = TargetReadback::decode(
+            wgpu::TextureFormat::Depth32Float,
+            1,
+            1,
+            &0.5f32.to_le_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            depth.to_rgba8(Channel::All, 0.0, 1.0),
+            vec![128, 128, 128, 255]
+        );
+        assert_eq!(depth.value_range(Channel::All), Some((0.5, 0.5)));
+    }
+}
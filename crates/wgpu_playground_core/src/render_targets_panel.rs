@@ -0,0 +1,753 @@
+use crate::asset_cache::format_bytes;
+use crate::device_recovery::ResourceRecreate;
+use crate::render_graph_analysis::{analyze, GraphAnalysis, TextureState};
+use crate::render_targets::{
+    read_target, Channel, GraphPass, PassContent, RenderGraph, RenderGraphExecutor,
+    RenderTargetDesc, RenderTargetError, RenderTargetManager, TargetReadback, COLOR_FORMATS,
+    DEPTH_FORMATS, MAX_TARGET_SIZE, SAMPLE_COUNTS,
+};
+
+/// Stored channels of a texel, labelled, e.g. `R 0.9020  G 0.2000`
+pub fn format_texel(texel: [f32; 4], format: wgpu::TextureFormat) -> String {
+    if format.is_depth_stencil_format() {
+        return format!("Depth {:.6}", texel[0]);
+    }
+    ["R", "G", "B", "A"]
+        .iter()
+        .zip(texel)
+        .take(format.components() as usize)
+        .map(|(name, value)| format!("{} {:.4}", name, value))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+fn names_where(descs: &[RenderTargetDesc], keep: impl Fn(&RenderTargetDesc) -> bool) -> Vec<&str> {
+    descs
+        .iter()
+        .filter(|desc| keep(desc))
+        .map(|desc| desc.name.as_str())
+        .collect()
+}
+
+/// The transitions wgpu inserts before pass `pass`, or after the graph
+fn transitions_ui(ui: &mut egui::Ui, analysis: &GraphAnalysis, pass: Option<usize>) {
+    for transition in analysis.transitions_before(pass) {
+        let color = match transition.from {
+            TextureState::Undefined => egui::Color32::GRAY,
+            _ => egui::Color32::from_rgb(230, 170, 60),
+        };
+        ui.colored_label(
+            color,
+            format!(
+                "⇣ {}: {} → {}",
+                transition.target,
+                transition.from.name(),
+                transition.to.name()
+            ),
+        )
+        .on_hover_text(transition.explanation());
+    }
+}
+
+/// Combo box choosing one of `names` or none; returns whether it changed
+fn target_combo(
+    ui: &mut egui::Ui,
+    id: impl std::hash::Hash,
+    slot: &mut Option<String>,
+    names: &[&str],
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(slot.as_deref().unwrap_or("None"))
+        .show_ui(ui, |ui| {
+            changed |= ui.selectable_value(slot, None, "None").changed();
+            for name in names {
+                changed |= ui
+                    .selectable_value(slot, Some(name.to_string()), *name)
+                    .changed();
+            }
+        });
+    changed
+}
+
+/// UI panel to create named off-screen render targets, wire them into a
+/// render graph as pass attachments and inspect what the passes drew
+pub struct RenderTargetsPanel {
+    targets: RenderTargetManager,
+    /// Targets created once a device is available
+    pending: Vec<RenderTargetDesc>,
+    graph: RenderGraph,
+    executor: Option<RenderGraphExecutor>,
+    /// Settings of the next target to create
+    new_target: RenderTargetDesc,
+    /// Run the graph and read the inspected target back on the next frame
+    dirty: bool,
+    error: Option<String>,
+    inspected: Option<String>,
+    channel: Channel,
+    /// Values mapped to black and white in the inspector
+    range: (f32, f32),
+    zoom: f32,
+    readback: Option<Result<TargetReadback, RenderTargetError>>,
+    /// Texel clicked in the inspector
+    picked: Option<(u32, u32)>,
+    image: Option<egui::TextureHandle>,
+    image_dirty: bool,
+    /// Annotate the passes with the transitions wgpu inserts between them
+    driver_overlay: bool,
+}
+
+impl Default for RenderTargetsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderTargetsPanel {
+    /// Start with the example graph, inspecting its post-processed target
+    pub fn new() -> Self {
+        let (pending, graph) = RenderGraph::example();
+        Self {
+            targets: RenderTargetManager::new(),
+            pending,
+            graph,
+            executor: None,
+            new_target: RenderTargetDesc::new("target", wgpu::TextureFormat::Rgba8Unorm, 256, 256),
+            dirty: true,
+            error: None,
+            inspected: Some("post".to_string()),
+            channel: Channel::All,
+            range: (0.0, 1.0),
+            zoom: 1.0,
+            readback: None,
+            picked: None,
+            image: None,
+            image_dirty: false,
+            driver_overlay: true,
+        }
+    }
+
+    pub fn targets(&self) -> &RenderTargetManager {
+        &self.targets
+    }
+
+    pub fn graph(&self) -> &RenderGraph {
+        &self.graph
+    }
+
+    pub fn set_graph(&mut self, graph: RenderGraph) {
+        self.graph = graph;
+        self.dirty = true;
+    }
+
+    /// Names of every target, including those not created yet
+    pub fn target_names(&self) -> Vec<&str> {
+        self.targets
+            .targets()
+            .iter()
+            .map(|target| target.name())
+            .chain(self.pending.iter().map(|desc| desc.name.as_str()))
+            .collect()
+    }
+
+    fn descs(&self) -> Vec<RenderTargetDesc> {
+        let mut descs = self.targets.descs();
+        descs.extend(self.pending.iter().cloned());
+        descs
+    }
+
+    /// Lifetimes, transitions and possible aliasing of the graph, with the
+    /// inspected target read back after it
+    pub fn analysis(&self) -> GraphAnalysis {
+        let exported: Vec<&str> = self.inspected.as_deref().into_iter().collect();
+        analyze(&self.graph, &self.descs(), &exported)
+    }
+
+    /// Create a target and run the graph again
+    pub fn add_target(
+        &mut self,
+        device: &wgpu::Device,
+        desc: RenderTargetDesc,
+    ) -> Result<(), RenderTargetError> {
+        self.targets.create(device, desc)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Remove a target and unbind it from every pass
+    pub fn remove_target(&mut self, name: &str) {
+        self.pending.retain(|desc| desc.name != name);
+        self.targets.remove(name);
+        self.graph.forget_target(name);
+        if self.inspected.as_deref() == Some(name) {
+            self.set_inspected(None);
+        }
+        self.dirty = true;
+    }
+
+    pub fn inspected(&self) -> Option<&str> {
+        self.inspected.as_deref()
+    }
+
+    /// Show `name` in the inspector
+    pub fn set_inspected(&mut self, name: Option<String>) {
+        if name != self.inspected {
+            self.inspected = name;
+            self.readback = None;
+            self.picked = None;
+            self.image = None;
+            self.dirty = true;
+        }
+    }
+
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    pub fn set_channel(&mut self, channel: Channel) {
+        if channel != self.channel {
+            self.channel = channel;
+            self.image_dirty = true;
+        }
+    }
+
+    pub fn picked(&self) -> Option<(u32, u32)> {
+        self.picked
+    }
+
+    /// The inspected target as last read back
+    pub fn readback(&self) -> Option<&TargetReadback> {
+        self.readback
+            .as_ref()
+            .and_then(|result| result.as_ref().ok())
+    }
+
+    /// Create pending targets, run the graph and read the inspected target
+    /// back
+    pub fn run(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.dirty = false;
+        self.error = None;
+        for desc in std::mem::take(&mut self.pending) {
+            if let Err(e) = self.targets.create(device, desc) {
+                self.error = Some(e.to_string());
+            }
+        }
+        let executor = self
+            .executor
+            .get_or_insert_with(|| RenderGraphExecutor::new(device));
+        if let Err(e) = executor.execute(device, queue, &self.graph, &self.targets) {
+            self.error = Some(e.to_string());
+        }
+        self.readback = self.inspected.as_deref().map(|name| {
+            let target = self
+                .targets
+                .get(name)
+                .ok_or_else(|| RenderTargetError::UnknownTarget(name.to_string()))?;
+            read_target(device, queue, target)
+        });
+        self.image_dirty = true;
+    }
+
+    /// Render the panel
+    pub fn ui(&mut self, ui: &mut egui::Ui, device: &wgpu::Device, queue: &wgpu::Queue) {
+        ui.heading("🎯 Render Targets");
+        ui.separator();
+        ui.label(
+            "Create off-screen color and depth targets, bind them as attachments of the passes \
+             in a render graph and inspect any single-sampled target channel by channel.",
+        );
+        ui.add_space(10.0);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::CollapsingHeader::new("Targets")
+                .default_open(true)
+                .show(ui, |ui| self.targets_ui(ui, device));
+            egui::CollapsingHeader::new("Render Graph")
+                .default_open(true)
+                .show(ui, |ui| self.graph_ui(ui));
+            egui::CollapsingHeader::new("What wgpu Does for You")
+                .default_open(false)
+                .show(ui, |ui| self.driver_ui(ui));
+            if let Some(error) = &self.error {
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+            }
+            ui.separator();
+
+            if self.dirty {
+                self.run(device, queue);
+            }
+            egui::CollapsingHeader::new("Inspector")
+                .default_open(true)
+                .show(ui, |ui| self.inspector_ui(ui));
+        });
+    }
+
+    fn targets_ui(&mut self, ui: &mut egui::Ui, device: &wgpu::Device) {
+        let mut remove = None;
+        let mut inspect = None;
+        egui::Grid::new("render_targets_list")
+            .num_columns(5)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Name");
+                ui.strong("Format");
+                ui.strong("Size");
+                ui.strong("Samples");
+                ui.end_row();
+                for desc in self.descs() {
+                    ui.monospace(&desc.name);
+                    ui.label(format!("{:?}", desc.format));
+                    ui.label(format!("{}×{}", desc.width, desc.height));
+                    ui.label(format!("{}x", desc.sample_count));
+                    ui.horizontal(|ui| {
+                        let can_inspect = desc.sample_count == 1;
+                        if ui
+                            .add_enabled(can_inspect, egui::Button::new("🔍"))
+                            .on_hover_text("Show in the inspector")
+                            .on_disabled_hover_text(
+                                "Multisampled targets can't be read back; inspect their resolve target",
+                            )
+                            .clicked()
+                        {
+                            inspect = Some(desc.name.clone());
+                        }
+                        if ui.button("🗑").on_hover_text("Remove target").clicked() {
+                            remove = Some(desc.name.clone());
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        if let Some(name) = remove {
+            self.remove_target(&name);
+        }
+        if let Some(name) = inspect {
+            self.set_inspected(Some(name));
+        }
+
+        ui.add_space(5.0);
+        ui.horizontal_wrapped(|ui| {
+            let desc = &mut self.new_target;
+            ui.label("New:");
+            ui.add(egui::TextEdit::singleline(&mut desc.name).desired_width(100.0));
+            egui::ComboBox::from_id_salt("render_target_format")
+                .selected_text(format!("{:?}", desc.format))
+                .show_ui(ui, |ui| {
+                    for format in COLOR_FORMATS.iter().chain(DEPTH_FORMATS) {
+                        ui.selectable_value(&mut desc.format, *format, format!("{:?}", format));
+                    }
+                });
+            ui.add(egui::DragValue::new(&mut desc.width).range(1..=MAX_TARGET_SIZE));
+            ui.label("×");
+            ui.add(egui::DragValue::new(&mut desc.height).range(1..=MAX_TARGET_SIZE));
+            egui::ComboBox::from_id_salt("render_target_samples")
+                .selected_text(format!("{}x", desc.sample_count))
+                .show_ui(ui, |ui| {
+                    for count in SAMPLE_COUNTS {
+                        ui.selectable_value(&mut desc.sample_count, count, format!("{}x", count));
+                    }
+                });
+            if ui.button("➕ Create").clicked() {
+                let desc = desc.clone();
+                if let Err(e) = self.add_target(device, desc) {
+                    self.error = Some(e.to_string());
+                }
+            }
+        });
+    }
+
+    fn graph_ui(&mut self, ui: &mut egui::Ui) {
+        let descs = self.descs();
+        let color_names = names_where(&descs, |desc| !desc.is_depth());
+        let resolve_names = names_where(&descs, |desc| !desc.is_depth() && desc.sample_count == 1);
+        let depth_names = names_where(&descs, RenderTargetDesc::is_depth);
+
+        let analysis = self.driver_overlay.then(|| self.analysis());
+        let mut changed = false;
+        let mut action = None;
+        let pass_count = self.graph.passes.len();
+        for (index, pass) in self.graph.passes.iter_mut().enumerate() {
+            if let Some(analysis) = &analysis {
+                transitions_ui(ui, analysis, Some(index));
+            }
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}.", index + 1));
+                    changed |= ui
+                        .add(egui::TextEdit::singleline(&mut pass.name).desired_width(120.0))
+                        .changed();
+                    if ui.add_enabled(index > 0, egui::Button::new("⬆")).clicked() {
+                        action = Some((index, -1));
+                    }
+                    if ui
+                        .add_enabled(index + 1 < pass_count, egui::Button::new("⬇"))
+                        .clicked()
+                    {
+                        action = Some((index, 1));
+                    }
+                    if ui.button("🗑").on_hover_text("Remove pass").clicked() {
+                        action = Some((index, 0));
+                    }
+                });
+                egui::Grid::new(("render_graph_pass", index))
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label("Color:");
+                        changed |=
+                            target_combo(ui, ("pass_color", index), &mut pass.color, &color_names);
+                        ui.end_row();
+                        ui.label("Resolve:").on_hover_text(
+                            "Single-sampled target a multisampled color attachment resolves into",
+                        );
+                        changed |= target_combo(
+                            ui,
+                            ("pass_resolve", index),
+                            &mut pass.resolve,
+                            &resolve_names,
+                        );
+                        ui.end_row();
+                        ui.label("Depth:");
+                        changed |=
+                            target_combo(ui, ("pass_depth", index), &mut pass.depth, &depth_names);
+                        ui.end_row();
+
+                        ui.label("Draws:");
+                        ui.horizontal(|ui| {
+                            let first_source = resolve_names.first().copied().unwrap_or_default();
+                            for content in [
+                                PassContent::Clear,
+                                PassContent::Shapes,
+                                PassContent::Sample(first_source.to_string()),
+                            ] {
+                                let selected = std::mem::discriminant(&pass.content)
+                                    == std::mem::discriminant(&content);
+                                if ui.selectable_label(selected, content.name()).clicked()
+                                    && !selected
+                                {
+                                    pass.content = content;
+                                    changed = true;
+                                }
+                            }
+                            if let PassContent::Sample(source) = &mut pass.content {
+                                egui::ComboBox::from_id_salt(("pass_source", index))
+                                    .selected_text(source.as_str())
+                                    .show_ui(ui, |ui| {
+                                        for name in &resolve_names {
+                                            changed |= ui
+                                                .selectable_value(source, name.to_string(), *name)
+                                                .changed();
+                                        }
+                                    });
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Clear color:");
+                        ui.horizontal(|ui| {
+                            changed |= ui
+                                .color_edit_button_rgba_unmultiplied(&mut pass.clear_color)
+                                .changed();
+                            changed |= ui
+                                .checkbox(&mut pass.keep_contents, "Keep contents")
+                                .on_hover_text("Load what earlier passes drew instead of clearing")
+                                .changed();
+                        });
+                        ui.end_row();
+                    });
+            });
+        }
+
+        match action {
+            Some((index, 0)) => {
+                self.graph.passes.remove(index);
+                changed = true;
+            }
+            Some((index, direction)) => {
+                let other = (index as isize + direction) as usize;
+                self.graph.passes.swap(index, other);
+                changed = true;
+            }
+            None => {}
+        }
+        if let Some(analysis) = &analysis {
+            transitions_ui(ui, analysis, None);
+        }
+        ui.horizontal(|ui| {
+            if ui.button("➕ Add Pass").clicked() {
+                let name = format!("Pass {}", self.graph.passes.len() + 1);
+                self.graph.passes.push(GraphPass::new(name));
+                changed = true;
+            }
+            if ui.button("▶ Run Graph").clicked() {
+                changed = true;
+            }
+        });
+        self.dirty |= changed;
+    }
+
+    fn driver_ui(&mut self, ui: &mut egui::Ui) {
+        ui.label(
+            "WebGPU has no barriers or image layouts: wgpu tracks how each target is used and \
+             inserts the transitions between passes itself. This replays the graph the same way.",
+        );
+        ui.checkbox(
+            &mut self.driver_overlay,
+            "Show the transitions between the passes above",
+        );
+        ui.add_space(5.0);
+
+        let analysis = self.analysis();
+        let passes = &self.graph.passes;
+        ui.strong("Lifetimes");
+        egui::Grid::new("render_graph_lifetimes")
+            .num_columns(passes.len() + 2)
+            .spacing([8.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                for pass in passes {
+                    ui.strong(&pass.name);
+                }
+                ui.strong("Size");
+                ui.end_row();
+                for lifetime in &analysis.lifetimes {
+                    ui.monospace(&lifetime.target);
+                    for (index, access) in lifetime.accesses.iter().enumerate() {
+                        match access {
+                            Some(access) => {
+                                ui.monospace(access.short()).on_hover_text(access.label());
+                            }
+                            None if (lifetime.first..=lifetime.last).contains(&index) => {
+                                ui.weak("│").on_hover_text("Contents kept for a later pass");
+                            }
+                            None => {
+                                ui.label("");
+                            }
+                        }
+                    }
+                    let size = ui.label(format_bytes(lifetime.bytes));
+                    if lifetime.could_discard() {
+                        size.on_hover_text(
+                            "Only this pass uses it: StoreOp::Discard would let tile-based \
+                             GPUs keep it in on-chip memory and never write it out",
+                        );
+                    } else if !lifetime.transient {
+                        size.on_hover_text("Contents are needed outside the graph");
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.weak("C color, R resolve, D depth, S sampled; │ kept alive between uses");
+        ui.add_space(5.0);
+
+        ui.strong("Aliasing");
+        if analysis.alias_groups.is_empty() {
+            ui.label("No two transient targets have disjoint lifetimes.");
+        }
+        for group in &analysis.alias_groups {
+            ui.label(format!(
+                "• {} could share one {} allocation",
+                group.targets.join(", "),
+                format_bytes(group.bytes)
+            ));
+        }
+        ui.label(format!(
+            "Memory: {} as allocated, {} with aliasing",
+            format_bytes(analysis.total_bytes()),
+            format_bytes(analysis.aliased_bytes())
+        ));
+        ui.weak(
+            "wgpu gives every texture its own memory; aliasing is what a frame graph with \
+             transient resources does on top of Vulkan, D3D12 or Metal.",
+        );
+    }
+
+    fn inspector_ui(&mut self, ui: &mut egui::Ui) {
+        let readable: Vec<String> = self
+            .descs()
+            .into_iter()
+            .filter(|desc| desc.sample_count == 1)
+            .map(|desc| desc.name)
+            .collect();
+        let mut inspected = self.inspected.clone();
+        ui.horizontal(|ui| {
+            ui.label("Target:");
+            let names: Vec<&str> = readable.iter().map(String::as_str).collect();
+            target_combo(ui, "inspected_target", &mut inspected, &names);
+            ui.separator();
+            for channel in Channel::ALL {
+                if ui
+                    .selectable_label(self.channel == channel, channel.name())
+                    .clicked()
+                {
+                    self.set_channel(channel);
+                }
+            }
+        });
+        self.set_inspected(inspected);
+
+        let readback = match &self.readback {
+            None => {
+                ui.label("Select a single-sampled target to inspect it");
+                return;
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::RED, e.to_string());
+                return;
+            }
+            Some(Ok(readback)) => readback,
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Range:");
+            let (min, max) = &mut self.range;
+            self.image_dirty |= ui.add(egui::DragValue::new(min).speed(0.01)).changed();
+            self.image_dirty |= ui.add(egui::DragValue::new(max).speed(0.01)).changed();
+            if ui
+                .button("Auto")
+                .on_hover_text("Stretch the smallest to the largest value shown")
+                .clicked()
+            {
+                if let Some(range) = readback.value_range(self.channel) {
+                    self.range = range;
+                    self.image_dirty = true;
+                }
+            }
+            if ui.button("0…1").clicked() {
+                self.range = (0.0, 1.0);
+                self.image_dirty = true;
+            }
+            ui.separator();
+            ui.add(
+                egui::Slider::new(&mut self.zoom, 0.25..=8.0)
+                    .logarithmic(true)
+                    .text("Zoom"),
+            );
+        });
+
+        if self.image_dirty || self.image.is_none() {
+            let pixels = readback.to_rgba8(self.channel, self.range.0, self.range.1);
+            let image = egui::ColorImage::from_rgba_unmultiplied(
+                [readback.width as usize, readback.height as usize],
+                &pixels,
+            );
+            self.image = Some(ui.ctx().load_texture(
+                "render_target_inspector",
+                image,
+                egui::TextureOptions::NEAREST,
+            ));
+            self.image_dirty = false;
+        }
+        let Some(image) = &self.image else {
+            return;
+        };
+
+        ui.label(format!(
+            "{:?}, {}×{}",
+            readback.format, readback.width, readback.height
+        ));
+        let size = egui::vec2(readback.width as f32, readback.height as f32) * self.zoom;
+        let mut picked = self.picked;
+        egui::ScrollArea::both()
+            .id_salt("render_target_image")
+            .max_height(400.0)
+            .show(ui, |ui| {
+                let response = ui.add(
+                    egui::Image::new(egui::load::SizedTexture::new(image.id(), size))
+                        .sense(egui::Sense::click()),
+                );
+                if let Some(pos) = response.hover_pos() {
+                    let texel = (pos - response.rect.min) / self.zoom;
+                    let (x, y) = (texel.x as u32, texel.y as u32);
+                    if let Some(value) = readback.texel(x, y) {
+                        if response.clicked() {
+                            picked = Some((x, y));
+                        }
+                        response.on_hover_text(format!(
+                            "({}, {}) {}",
+                            x,
+                            y,
+                            format_texel(value, readback.format)
+                        ));
+                    }
+                }
+            });
+        self.picked = picked;
+
+        if let Some((x, y)) = self.picked {
+            if let Some(value) = readback.texel(x, y) {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Picked ({}, {}):", x, y));
+                    ui.monospace(format_texel(value, readback.format));
+                });
+            }
+        } else {
+            ui.weak("Hover a texel to read it, click to keep its value");
+        }
+    }
+}
+
+impl ResourceRecreate for RenderTargetsPanel {
+    fn recreate_resources(&mut self, device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // Targets are created again with the same descriptions; their
+        // contents and the pipelines come back when the graph runs again
+        self.targets.recreate(device);
+        self.executor = None;
+        self.readback = None;
+        self.image = None;
+        self.dirty = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_target_unbinds_it() {
+        let mut panel = RenderTargetsPanel::new();
+        assert!(panel.target_names().contains(&"scene"));
+        assert_eq!(panel.inspected(), Some("post"));
+
+        panel.remove_target("post");
+        assert!(!panel.target_names().contains(&"post"));
+        assert_eq!(panel.inspected(), None);
+        assert_eq!(panel.graph().passes[1].color, None);
+
+        panel.remove_target("scene");
+        assert_eq!(panel.graph().passes[0].resolve, None);
+        assert_eq!(panel.graph().passes[1].content, PassContent::Clear);
+    }
+
+    #[test]
+    fn test_analysis_reads_back_the_inspected_target() {
+        let mut panel = RenderTargetsPanel::new();
+        let analysis = panel.analysis();
+        assert!(!analysis.lifetime("post").unwrap().transient);
+        assert_eq!(analysis.transitions_before(None).count(), 1);
+
+        panel.set_inspected(None);
+        let analysis = panel.analysis();
+        assert!(analysis.lifetime("post").unwrap().transient);
+        assert_eq!(analysis.transitions_before(None).count(), 0);
+    }
+
+    #[test]
+    fn test_format_texel() {
+        let texel = [0.5, 0.25, 0.0, 1.0];
+        assert_eq!(
+            format_texel(texel, wgpu::TextureFormat::Rgba8Unorm),
+            "R 0.5000  G 0.2500  B 0.0000  A 1.0000"
+        );
+        assert_eq!(
+            format_texel(texel, wgpu::TextureFormat::R32Float),
+            "R 0.5000"
+        );
+        assert_eq!(
+            format_texel(texel, wgpu::TextureFormat::Depth32Float),
+            "Depth 0.500000"
+        );
+    }
+}
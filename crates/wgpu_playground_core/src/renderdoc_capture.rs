@@ -0,0 +1,295 @@
+//! Frame captures through the RenderDoc in-application API
+//!
+//! When the playground is launched from RenderDoc, the `renderdoc` feature
+//! lets the GUI capture the next frame: [`RenderDocCapture::request_capture`]
+//! arms a capture, the render loop brackets the following frame with
+//! [`RenderDocCapture::begin_frame`] and [`RenderDocCapture::end_frame`], and
+//! the capture file is tagged with the panel and pipeline that were active
+//! when it was requested. The API is only present in a process RenderDoc
+//! injected itself into; otherwise loading fails and the capture controls
+//! stay disabled.
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// Error of the RenderDoc integration
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderDocError {
+    /// Built without the `renderdoc` feature, or for the web
+    Unsupported,
+    /// The in-application API could not be loaded
+    NotLoaded(String),
+    /// The frame ended without RenderDoc reporting a capture
+    NoCapture,
+    /// The replay UI could not be started
+    ReplayUi(String),
+}
+
+impl fmt::Display for RenderDocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderDocError::Unsupported => {
+                write!(
+                    f,
+                    "RenderDoc captures need a native build with the `renderdoc` feature"
+                )
+            }
+            RenderDocError::NotLoaded(msg) => write!(
+                f,
+                "RenderDoc is not attached ({}); launch the playground from RenderDoc",
+                msg
+            ),
+            RenderDocError::NoCapture => write!(f, "RenderDoc did not record a capture"),
+            RenderDocError::ReplayUi(msg) => {
+                write!(f, "Failed to launch the RenderDoc replay UI: {}", msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderDocError {}
+
+/// Label a capture with the panel it was taken from and, if there is one,
+/// the pipeline or example that panel shows
+pub fn capture_label(panel: &str, detail: Option<&str>) -> String {
+    match detail.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(detail) => format!("{}: {}", panel, detail),
+        None => panel.to_string(),
+    }
+}
+
+/// A capture written by RenderDoc
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureInfo {
+    pub path: PathBuf,
+    pub label: String,
+}
+
+/// Captures of single frames, requested from the GUI
+pub struct RenderDocCapture {
+    #[cfg(all(feature = "renderdoc", not(target_arch = "wasm32")))]
+    api: Option<backend::Api>,
+    load_error: Option<RenderDocError>,
+    /// Label of the capture to take next frame
+    requested: Option<String>,
+    /// Label of the capture in progress
+    capturing: Option<String>,
+    captures: Vec<CaptureInfo>,
+}
+
+impl Default for RenderDocCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderDocCapture {
+    /// Load the in-application API of an attached RenderDoc
+    pub fn new() -> Self {
+        #[cfg(all(feature = "renderdoc", not(target_arch = "wasm32")))]
+        {
+            let (api, load_error) = match backend::Api::load() {
+                Ok(api) => (Some(api), None),
+                Err(e) => (None, Some(RenderDocError::NotLoaded(e))),
+            };
+            Self {
+                api,
+                load_error,
+                requested: None,
+                capturing: None,
+                captures: Vec::new(),
+            }
+        }
+        #[cfg(not(all(feature = "renderdoc", not(target_arch = "wasm32"))))]
+        {
+            Self {
+                load_error: Some(RenderDocError::Unsupported),
+                requested: None,
+                capturing: None,
+                captures: Vec::new(),
+            }
+        }
+    }
+
+    /// Whether captures can be taken
+    pub fn is_available(&self) -> bool {
+        self.load_error.is_none()
+    }
+
+    /// Why captures cannot be taken
+    pub fn load_error(&self) -> Option<&RenderDocError> {
+        self.load_error.as_ref()
+    }
+
+    /// Capture the next frame, tagged with `label`
+    pub fn request_capture(&mut self, label: impl Into<String>) -> Result<(), RenderDocError> {
+        if let Some(error) = &self.load_error {
+            return Err(error.clone());
+        }
+        self.requested = Some(label.into());
+        Ok(())
+    }
+
+    /// Whether a capture is waiting for the next frame or in progress
+    pub fn is_pending(&self) -> bool {
+        self.requested.is_some() || self.capturing.is_some()
+    }
+
+    /// Start capturing if a capture was requested; call before the frame's
+    /// work is recorded
+    pub fn begin_frame(&mut self) {
+        if self.capturing.is_some() {
+            return;
+        }
+        let Some(label) = self.requested.take() else {
+            return;
+        };
+        #[cfg(all(feature = "renderdoc", not(target_arch = "wasm32")))]
+        if let Some(api) = &mut self.api {
+            api.start();
+        }
+        self.capturing = Some(label);
+    }
+
+    /// Finish a capture begun this frame; call after presenting
+    ///
+    /// Returns the capture, or `None` if none was in progress.
+    pub fn end_frame(&mut self) -> Option<Result<CaptureInfo, RenderDocError>> {
+        let label = self.capturing.take()?;
+        let result = self.finish(label);
+        if let Ok(info) = &result {
+            self.captures.push(info.clone());
+        }
+        Some(result)
+    }
+
+    #[cfg(all(feature = "renderdoc", not(target_arch = "wasm32")))]
+    fn finish(&mut self, label: String) -> Result<CaptureInfo, RenderDocError> {
+        let api = self.api.as_mut().ok_or(RenderDocError::Unsupported)?;
+        let path = api.end().ok_or(RenderDocError::NoCapture)?;
+        api.set_comments(&path, &label);
+        Ok(CaptureInfo { path, label })
+    }
+
+    #[cfg(not(all(feature = "renderdoc", not(target_arch = "wasm32"))))]
+    fn finish(&mut self, _label: String) -> Result<CaptureInfo, RenderDocError> {
+        Err(RenderDocError::Unsupported)
+    }
+
+    /// Captures taken this session, oldest first
+    pub fn captures(&self) -> &[CaptureInfo] {
+        &self.captures
+    }
+
+    /// Open RenderDoc's replay UI connected to this process
+    pub fn launch_replay_ui(&self) -> Result<(), RenderDocError> {
+        if let Some(error) = &self.load_error {
+            return Err(error.clone());
+        }
+        #[cfg(all(feature = "renderdoc", not(target_arch = "wasm32")))]
+        {
+            let api = self.api.as_ref().ok_or(RenderDocError::Unsupported)?;
+            api.launch_replay_ui().map_err(RenderDocError::ReplayUi)
+        }
+        #[cfg(not(all(feature = "renderdoc", not(target_arch = "wasm32"))))]
+        {
+            Err(RenderDocError::Unsupported)
+        }
+    }
+}
+
+/// The in-application API through the renderdoc crate
+#[cfg(all(feature = "renderdoc", not(target_arch = "wasm32")))]
+mod backend {
+    use std::path::{Path, PathBuf};
+
+    use renderdoc::{RenderDoc, V141};
+
+    pub struct Api(RenderDoc<V141>);
+
+    impl Api {
+        pub fn load() -> Result<Self, String> {
+            RenderDoc::new().map(Api).map_err(|e| e.to_string())
+        }
+
+        /// Null device and window pointers capture whatever the frame
+        /// renders to
+        pub fn start(&mut self) {
+            self.0
+                .start_frame_capture(std::ptr::null(), std::ptr::null());
+        }
+
+        /// End the capture and return the file RenderDoc wrote
+        pub fn end(&mut self) -> Option<PathBuf> {
+            self.0.end_frame_capture(std::ptr::null(), std::ptr::null());
+            let newest = self.0.get_num_captures().checked_sub(1)?;
+            self.0.get_capture(newest).map(|(path, _)| path)
+        }
+
+        pub fn set_comments(&mut self, path: &Path, comments: &str) {
+            self.0.set_capture_file_comments(path.to_str(), comments);
+        }
+
+        pub fn launch_replay_ui(&self) -> Result<(), String> {
+            self.0
+                .launch_replay_ui(true, None)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_label() {
+        assert_eq!(
+            capture_label("Rendering", Some("Triangle")),
+            "Rendering: Triangle"
+        );
+        assert_eq!(
+            capture_label("Render Pipeline", Some("  ")),
+            "Render Pipeline"
+        );
+        assert_eq!(capture_label("Console", None), "Console");
+    }
+
+    #[cfg(not(feature = "renderdoc"))]
+    #[test]
+    fn test_captures_need_the_feature() {
+        let mut capture = RenderDocCapture::new();
+        assert!(!capture.is_available());
+        assert_eq!(
+            capture.request_capture("Rendering"),
+            Err(RenderDocError::Unsupported)
+        );
+        assert!(!capture.is_pending());
+        capture.begin_frame();
+        assert_eq!(capture.end_frame(), None);
+        assert_eq!(capture.launch_replay_ui(), Err(RenderDocError::Unsupported));
+    }
+
+    #[test]
+    fn test_capture_spans_the_frame_after_the_request() {
+        // Without an attached RenderDoc only the bookkeeping runs
+        let mut capture = RenderDocCapture::new();
+        capture.load_error = None;
+        capture.request_capture("Rendering: Triangle").unwrap();
+        assert!(capture.is_pending());
+        assert_eq!(capture.end_frame(), None);
+
+        capture.begin_frame();
+        assert_eq!(capture.capturing.as_deref(), Some("Rendering: Triangle"));
+        // A request during the captured frame waits for the next one
+        capture.request_capture("Console").unwrap();
+        capture.begin_frame();
+        assert_eq!(capture.capturing.as_deref(), Some("Rendering: Triangle"));
+
+        assert!(capture.end_frame().unwrap().is_err());
+        assert!(capture.captures().is_empty());
+        capture.begin_frame();
+        assert_eq!(capture.capturing.as_deref(), Some("Console"));
+    }
+}
@@ -1,8 +1,51 @@
 use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::assets::{self, AssetKind};
+use crate::bindless::{binding_array_support, MaterialGrid, MaterialPath, MATERIAL_COUNT};
+use crate::bitonic_sort::{
+    snapshot_rgba, BitonicSorter, KeyType, SortComparison, SortParams, MAX_KEYS, MIN_KEYS,
+};
+use crate::boids::{canvas_to_world, Flock, MAX_BOIDS, MAX_OBSTACLES};
+use crate::camera::Camera;
+use crate::command_palette::{Command, CommandKind, CommandRegistry};
+use crate::device_recovery::ResourceRecreate;
 use crate::example_metadata::get_example_api_tags;
 use crate::examples::{get_all_examples, Example, ExampleCategory};
-use crate::math_utils::{cross, dot, normalize};
+use crate::fluid::{FluidSim, FluidView, MAX_PRESSURE_ITERATIONS};
+use crate::grass::{CoverageMode, GrassField, FIELD_SIZE, MAX_BLADES};
+use crate::hardware_ray_tracing::{HardwareRayTracingError, RayQueryScene, RayQueryView};
+use crate::i18n::tr_args;
+use crate::image_processing::{
+    test_pattern, FilterParams, ImageFilter, ImageProcessor, MAX_BLUR_RADIUS,
+};
+use crate::instanced_cubes::{grid_radius, DrawPath, InstancedCubes, MAX_INSTANCES};
+use crate::lod::{LodScene, LodView, SelectionPath, LOD_COUNT};
+use crate::math_utils::{mat4_mul, rotation_x, rotation_y, MAT4_IDENTITY};
+use crate::mesh_shading::{
+    torus_knot, MeshShadingError, MeshletMesh, MeshletRenderer, MeshletSettings,
+};
+use crate::operation_errors::OperationErrors;
+use crate::parallel_algorithms::{
+    Algorithm, AlgorithmParams, AlgorithmRunner, Comparison, ReduceOp, MAX_ELEMENTS, MIN_ELEMENTS,
+};
+use crate::pixel_picker::PixelPicker;
+use crate::ray_tracer::{RayTracer, DEFAULT_MODEL, MAX_BOUNCES};
+use crate::render_pipeline::CullMode;
 use crate::shader_editor::ShaderEditor;
+use crate::shadow_bias::{
+    constant_bias_unit, ShadowScene, ShadowView, BIAS_PRESETS, SHADOW_FORMATS, SHADOW_MAP_SIZES,
+    SHADOW_PIPELINE,
+};
+use crate::source_viewer::SourceViewer;
+use crate::terrain::{
+    terrain_height, triangles_per_frame, vertices_per_frame, Terrain, HEIGHTMAP_SIZE, LEVELS,
+    MAX_OCTAVES,
+};
+use crate::text::TextScene;
+use crate::theme::{self, BackdropPattern, ThemePalette};
+use crate::tile_streaming::{TileStreamer, MIP_COUNT, WORLD_SIZE};
+use crate::upload_belt;
+use crate::volumetric_fog::{FogJitter, FogScene, FogView, FOG_HEIGHT};
+use crate::water::{WaterScene, WaterView, WATER_SIZE};
 use wgpu::{Device, Queue};
 
 /// Get color for API category badge
@@ -23,6 +66,59 @@ fn category_badge_color(category: &ApiCategory) -> egui::Color32 {
     }
 }
 
+/// Whether an example draws a 3D scene through [`Camera`]
+fn uses_camera(example_id: &str) -> bool {
+    matches!(
+        example_id,
+        "cube"
+            | "compute_grass"
+            | "terrain"
+            | "text_rendering"
+            | "instanced_cubes"
+            | "bindless_materials"
+            | "lod_meshes"
+            | "tile_streaming"
+            | "ray_tracing"
+            | "hardware_ray_tracing"
+            | "mesh_shading"
+            | "water"
+            | "volumetric_fog"
+            | "shadow_bias"
+    )
+}
+
+/// Whether an example has a preview the panel can run
+fn has_interactive_demo(example: &Example) -> bool {
+    let runnable_category = matches!(
+        example.category,
+        ExampleCategory::Rendering | ExampleCategory::ImageProcessing | ExampleCategory::Algorithms
+    );
+    let has_implementation = matches!(
+        example.id,
+        "triangle"
+            | "cube"
+            | "texture_mapping"
+            | "instanced_cubes"
+            | "bindless_materials"
+            | "lod_meshes"
+            | "compute_grass"
+            | "terrain"
+            | "text_rendering"
+            | "boids"
+            | "fluid_simulation"
+            | "water"
+            | "volumetric_fog"
+            | "shadow_bias"
+            | "tile_streaming"
+            | "ray_tracing"
+            | "hardware_ray_tracing"
+            | "mesh_shading"
+            | "bitonic_sort"
+    ) || ImageFilter::from_example_id(example.id).is_some()
+        || Algorithm::from_example_id(example.id).is_some();
+    runnable_category && has_implementation
+}
+
 /// Get a brief description for each API category
 fn get_api_category_description(category: &ApiCategory) -> &'static str {
     match category {
@@ -130,23 +226,170 @@ struct TextureState {
     sampler: wgpu::Sampler,
 }
 
+struct ImageProcessingState {
+    processor: ImageProcessor,
+    filter: ImageFilter,
+    /// Whether the output is out of date with the image or the parameters
+    dirty: bool,
+}
+
+struct AlgorithmState {
+    runner: AlgorithmRunner,
+    algorithm: Algorithm,
+    /// Result of the last run, shown in place of the canvas
+    comparison: Option<Comparison>,
+    error: Option<String>,
+}
+
+impl AlgorithmState {
+    /// Time both strategies against the CPU with `params`
+    fn run(&mut self, device: &Device, queue: &Queue, params: AlgorithmParams) {
+        match self.runner.compare(device, queue, self.algorithm, params) {
+            Ok(comparison) => {
+                self.comparison = Some(comparison);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+struct SortState {
+    sorter: BitonicSorter,
+    /// Result of the last run, shown in place of the canvas
+    comparison: Option<SortComparison>,
+    /// Keys of the visualization after every step, and their key type
+    snapshots: Vec<Vec<u32>>,
+    snapshot_key_type: Option<KeyType>,
+    /// Texture of the snapshots, uploaded when they change
+    image: Option<egui::TextureHandle>,
+    error: Option<String>,
+}
+
+impl SortState {
+    /// Time both strategies against the CPU with `params`, and visualize
+    /// the steps for its key type
+    fn run(&mut self, device: &Device, queue: &Queue, params: SortParams) {
+        if self.snapshot_key_type != Some(params.key_type) {
+            match self.sorter.snapshots(device, queue, params.key_type) {
+                Ok(snapshots) => {
+                    self.snapshots = snapshots;
+                    self.snapshot_key_type = Some(params.key_type);
+                    self.image = None;
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    return;
+                }
+            }
+        }
+        match self.sorter.compare(device, queue, params) {
+            Ok(comparison) => {
+                self.comparison = Some(comparison);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+struct InstancedState {
+    cubes: InstancedCubes,
+    time: f32,
+}
+
+struct BindlessState {
+    grid: MaterialGrid,
+    time: f32,
+}
+
+struct GrassState {
+    field: GrassField,
+    time: f32,
+}
+
+struct WaterState {
+    scene: WaterScene,
+    time: f32,
+}
+
+struct FogState {
+    scene: FogScene,
+    time: f32,
+}
+
+struct RayTracingState {
+    tracer: RayTracer,
+    /// Model filename under `assets/models`, editable in the controls
+    model: String,
+    error: Option<String>,
+}
+
+struct HardwareRayTracingState {
+    /// The scene, or why this device can't trace rays in hardware
+    scene: Result<RayQueryScene, HardwareRayTracingError>,
+}
+
+struct MeshShadingState {
+    mesh: MeshletMesh,
+    /// The renderer, or why this device can't run mesh shaders
+    renderer: Result<MeshletRenderer, MeshShadingError>,
+    settings: MeshletSettings,
+    /// Meshlets passing the culling tests, counted on the CPU
+    visible: usize,
+}
+
+impl RayTracingState {
+    fn load(&mut self, device: &Device) -> Result<(), String> {
+        let path = assets::resolve(AssetKind::Model, &self.model).map_err(|e| e.to_string())?;
+        self.tracer
+            .load_model(device, &path)
+            .map_err(|e| format!("{}: {}", self.model, e))
+    }
+
+    fn framing(&self) -> Camera {
+        if self.model == DEFAULT_MODEL {
+            // Close enough that the objects fill the view; the ground plane
+            // would push a bounds-based framing far away
+            return Camera::framing([0.0, 1.0, 0.0], 3.5);
+        }
+        let (min, max) = self.tracer.mesh().bounds();
+        let center = std::array::from_fn(|i| (min[i] + max[i]) * 0.5);
+        let radius = (0..3)
+            .map(|i| (max[i] - min[i]) * 0.5)
+            .fold(0.0f32, |sum, half| sum + half * half)
+            .sqrt();
+        Camera::framing(center, radius)
+    }
+}
+
 enum RenderState {
     None,
     Triangle(Box<TriangleState>),
     Cube(Box<CubeState>),
     Texture(Box<TextureState>),
+    Instanced(Box<InstancedState>),
+    Bindless(Box<BindlessState>),
+    Lod(Box<LodScene>),
+    Grass(Box<GrassState>),
+    Terrain(Box<Terrain>),
+    Text(Box<TextScene>),
+    Boids(Box<Flock>),
+    Fluid(Box<FluidSim>),
+    Water(Box<WaterState>),
+    Fog(Box<FogState>),
+    Shadow(Box<ShadowScene>),
+    TileStreaming(Box<TileStreamer>),
+    RayTracing(Box<RayTracingState>),
+    HardwareRayTracing(Box<HardwareRayTracingState>),
+    MeshShading(Box<MeshShadingState>),
+    ImageProcessing(Box<ImageProcessingState>),
+    Algorithms(Box<AlgorithmState>),
+    Sort(Box<SortState>),
 }
 
 impl RenderState {
-    fn update(
-        &mut self,
-        queue: &Queue,
-        delta_time: f32,
-        camera_distance: f32,
-        camera_rot_x: f32,
-        camera_rot_y: f32,
-        aspect: f32,
-    ) {
+    fn update(&mut self, queue: &Queue, delta_time: f32, camera: &Camera, aspect: f32) {
         if let RenderState::Cube(cube_state) = self {
             cube_state.time += delta_time;
 
@@ -158,27 +401,77 @@ impl RenderState {
                 model: [[f32; 4]; 4],
             }
 
-            let projection = perspective_matrix(45.0_f32.to_radians(), aspect, 0.1, 100.0);
-
-            // Calculate camera position based on rotation and distance
-            let cam_x = camera_distance * camera_rot_y.sin() * camera_rot_x.cos();
-            let cam_y = camera_distance * camera_rot_x.sin();
-            let cam_z = camera_distance * camera_rot_y.cos() * camera_rot_x.cos();
-
-            let view = look_at_matrix([cam_x, cam_y, cam_z], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
-            let view_proj = matrix_multiply(&projection, &view);
-
-            let rotation_y = rotation_y_matrix(cube_state.time);
-            let rotation_x = rotation_x_matrix(cube_state.time * 0.5);
-            let model = matrix_multiply(&rotation_y, &rotation_x);
+            let view_proj = camera.view_projection(aspect);
+            let model = mat4_mul(
+                &rotation_y(cube_state.time),
+                &rotation_x(cube_state.time * 0.5),
+            );
 
             let uniforms = Uniforms { view_proj, model };
 
-            queue.write_buffer(
+            upload_belt::write_buffer(
+                queue,
                 &cube_state.uniform_buffer,
                 0,
                 bytemuck::cast_slice(&[uniforms]),
             );
+        } else if let RenderState::Instanced(state) = self {
+            state.time += delta_time;
+            state
+                .cubes
+                .update(queue, camera.view_projection(aspect), state.time);
+        } else if let RenderState::Bindless(state) = self {
+            state.time += delta_time;
+            state
+                .grid
+                .update(queue, camera.view_projection(aspect), state.time);
+        } else if let RenderState::Grass(state) = self {
+            state.time += delta_time;
+            state
+                .field
+                .update(queue, camera.view_projection(aspect), state.time);
+        } else if let RenderState::Terrain(terrain) = self {
+            terrain.update(camera.view_projection(aspect), camera.eye());
+        } else if let RenderState::Text(scene) = self {
+            scene.update(camera.view_projection(aspect));
+        } else if let RenderState::Boids(flock) = self {
+            flock.update(queue, aspect, delta_time);
+        } else if let RenderState::Fluid(sim) = self {
+            sim.update(queue, delta_time);
+        } else if let RenderState::Water(state) = self {
+            state.time += delta_time;
+            state.scene.update(
+                queue,
+                camera.view_projection(aspect),
+                camera.eye(),
+                state.time,
+            );
+        } else if let RenderState::Fog(state) = self {
+            state.time += delta_time;
+            state.scene.update(
+                queue,
+                camera.view_projection(aspect),
+                camera.eye(),
+                state.time,
+            );
+        } else if let RenderState::Shadow(scene) = self {
+            scene.update(queue, camera.view_projection(aspect));
+        } else if let RenderState::RayTracing(state) = self {
+            state
+                .tracer
+                .update(camera.view_projection(aspect), camera.eye());
+        } else if let RenderState::HardwareRayTracing(state) = self {
+            if let Ok(scene) = &mut state.scene {
+                scene.update(camera.view_projection(aspect), camera.eye(), delta_time);
+            }
+        } else if let RenderState::MeshShading(state) = self {
+            let view_proj = camera.view_projection(aspect);
+            state.visible = state
+                .mesh
+                .count_visible(&view_proj, camera.eye(), &state.settings);
+            if let Ok(renderer) = &mut state.renderer {
+                renderer.update(view_proj, camera.eye());
+            }
         }
     }
 }
@@ -187,6 +480,7 @@ pub struct RenderingPanel {
     examples: Vec<Example>,
     selected_example: Option<usize>,
     show_source_code: bool,
+    source_viewer: SourceViewer,
     category_filter: Option<ExampleCategory>,
     render_state: RenderState,
     render_texture: Option<wgpu::Texture>,
@@ -199,17 +493,36 @@ pub struct RenderingPanel {
     canvas_width: u32,
     canvas_height: u32,
     clear_color: [f32; 4],
+    /// Pattern shown through transparent parts of the preview
+    preview_backdrop: BackdropPattern,
     // Camera control for 3D examples
-    camera_distance: f32,
-    camera_rotation_x: f32,
-    camera_rotation_y: f32,
+    camera: Camera,
+    /// Shift-hover readback of the canvas texels
+    pixel_picker: PixelPicker,
+    // Image processing examples
+    filter_params: FilterParams,
+    /// Fraction of the preview width showing the unfiltered image
+    split_position: f32,
+    /// Encoded image from the texture panel; the test pattern is used without one
+    processing_image: Option<Vec<u8>>,
+    processing_image_changed: bool,
+    processing_error: Option<String>,
+    // Algorithm examples
+    algorithm_params: AlgorithmParams,
+    sort_params: SortParams,
     // Track if we've auto-started an example
     first_render: bool,
     // Code export
     export_project_name: String,
+    export_target: crate::code_generator::ExportTarget,
     export_status_message: Option<(String, bool)>, // (message, is_success)
+    // Device errors from starting an example
+    gpu_errors: OperationErrors,
 }
 
+/// Operation name of starting an example in [`OperationErrors`]
+const START_EXAMPLE: &str = "start_example";
+
 impl Default for RenderingPanel {
     fn default() -> Self {
         Self::new_without_device()
@@ -228,6 +541,7 @@ impl RenderingPanel {
             examples: get_all_examples(),
             selected_example: Some(0), // Auto-select first example (triangle)
             show_source_code: false,
+            source_viewer: SourceViewer::new(),
             category_filter: None,
             render_state: RenderState::None,
             render_texture: None,
@@ -238,13 +552,22 @@ impl RenderingPanel {
             show_shader_editor: false,
             canvas_width: 512,
             canvas_height: 512,
-            clear_color: [0.05, 0.05, 0.1, 1.0],
-            camera_distance: 3.0,
-            camera_rotation_x: 0.0,
-            camera_rotation_y: 0.0,
+            clear_color: theme::current().preview_clear,
+            preview_backdrop: theme::current().backdrop,
+            camera: Camera::new(),
+            pixel_picker: PixelPicker::new(),
+            filter_params: FilterParams::default(),
+            split_position: 0.5,
+            processing_image: None,
+            processing_image_changed: false,
+            processing_error: None,
+            algorithm_params: AlgorithmParams::default(),
+            sort_params: SortParams::default(),
             first_render: true, // Mark that this is the first render
             export_project_name: "wgpu_standalone".to_string(),
+            export_target: crate::code_generator::ExportTarget::Native,
             export_status_message: None,
+            gpu_errors: OperationErrors::new(),
         }
     }
 
@@ -277,6 +600,212 @@ impl RenderingPanel {
         self.render_texture_id = None;
     }
 
+    /// Create the render state of an example, stopping it if the device rejects it
+    fn start_example(&mut self, device: &Device, queue: &Queue, example_id: &str) {
+        let gpu_errors = self.gpu_errors.clone();
+        gpu_errors.scoped(device, START_EXAMPLE, || match example_id {
+            "triangle" => self.create_triangle_render_state(device, queue),
+            "cube" => {
+                self.camera = Camera::new();
+                self.create_cube_render_state(device, queue);
+            }
+            "texture_mapping" => self.create_texture_mapping_render_state(device, queue),
+            "instanced_cubes" => {
+                let cubes = InstancedCubes::new(
+                    device,
+                    queue,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                self.camera = Camera::framing([0.0; 3], grid_radius(cubes.instance_count()));
+                self.render_state =
+                    RenderState::Instanced(Box::new(InstancedState { cubes, time: 0.0 }));
+            }
+            "bindless_materials" => {
+                let grid =
+                    MaterialGrid::new(device, queue, wgpu::TextureFormat::Bgra8UnormSrgb, true);
+                self.camera = Camera::framing([0.0; 3], crate::bindless::grid_radius());
+                self.render_state =
+                    RenderState::Bindless(Box::new(BindlessState { grid, time: 0.0 }));
+            }
+            "lod_meshes" => {
+                let scene = LodScene::new(
+                    device,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                // Close enough that the nearest spheres need the finest mesh
+                self.camera = Camera::framing([0.0; 3], scene.scene_radius() / 4.0);
+                self.render_state = RenderState::Lod(Box::new(scene));
+            }
+            "compute_grass" => {
+                let field = GrassField::new(
+                    device,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                // Close to the ground, where the blade edges are large
+                self.camera = Camera::framing([0.0; 3], FIELD_SIZE / 4.0);
+                self.render_state = RenderState::Grass(Box::new(GrassState { field, time: 0.0 }));
+            }
+            "terrain" => {
+                let terrain = Terrain::new(
+                    device,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                // Above the middle of the map, with the far levels in view
+                let ground = terrain_height(&terrain.noise, 0.0, 0.0);
+                self.camera = Camera::framing([0.0, ground, 0.0], 40.0);
+                self.render_state = RenderState::Terrain(Box::new(terrain));
+            }
+            "text_rendering" => {
+                let scene = TextScene::new(device, wgpu::TextureFormat::Bgra8UnormSrgb);
+                // The labelled cube spans -1..1
+                self.camera = Camera::framing([0.0; 3], 2.0);
+                self.render_state = RenderState::Text(Box::new(scene));
+            }
+            "boids" => {
+                let flock = Flock::new(
+                    device,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width as f32 / self.canvas_height as f32,
+                );
+                self.render_state = RenderState::Boids(Box::new(flock));
+            }
+            "fluid_simulation" => {
+                let sim = FluidSim::new(
+                    device,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width as f32 / self.canvas_height as f32,
+                );
+                self.render_state = RenderState::Fluid(Box::new(sim));
+            }
+            "water" => {
+                let scene = WaterScene::new(
+                    device,
+                    queue,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                // The whole pool in view
+                self.camera = Camera::framing([0.0; 3], WATER_SIZE / 2.0);
+                self.render_state = RenderState::Water(Box::new(WaterState { scene, time: 0.0 }));
+            }
+            "volumetric_fog" => {
+                let scene = FogScene::new(
+                    device,
+                    queue,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                // Looking down across the fog layer at the pillars
+                self.camera = Camera::framing([0.0, FOG_HEIGHT / 2.0, 0.0], 10.0);
+                self.render_state = RenderState::Fog(Box::new(FogState { scene, time: 0.0 }));
+            }
+            "shadow_bias" => {
+                let scene = ShadowScene::new(
+                    device,
+                    queue,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                // Low over the ground, where the acne stripes are wide
+                self.camera = Camera::framing([0.0, 1.0, 0.0], 9.0);
+                self.render_state = RenderState::Shadow(Box::new(scene));
+            }
+            "tile_streaming" => {
+                let streamer =
+                    TileStreamer::new(device, queue, wgpu::TextureFormat::Bgra8UnormSrgb);
+                // Close enough to the ground that the near tiles need mip 0
+                self.camera = Camera::framing([0.0; 3], WORLD_SIZE / 8.0);
+                self.render_state = RenderState::TileStreaming(Box::new(streamer));
+            }
+            "ray_tracing" => {
+                let mut state = RayTracingState {
+                    tracer: RayTracer::new(
+                        device,
+                        wgpu::TextureFormat::Bgra8UnormSrgb,
+                        self.canvas_width,
+                        self.canvas_height,
+                    ),
+                    model: DEFAULT_MODEL.to_string(),
+                    error: None,
+                };
+                state.error = state.load(device).err();
+                self.camera = state.framing();
+                self.render_state = RenderState::RayTracing(Box::new(state));
+            }
+            "hardware_ray_tracing" => {
+                let scene = RayQueryScene::new(device, queue, wgpu::TextureFormat::Bgra8UnormSrgb);
+                if let Err(e) = &scene {
+                    log::warn!("{}", e);
+                }
+                // Wide enough for the ring of cubes and the row of spheres behind
+                self.camera = Camera::framing([0.0, 1.0, -1.0], 5.5);
+                self.render_state =
+                    RenderState::HardwareRayTracing(Box::new(HardwareRayTracingState { scene }));
+            }
+            "mesh_shading" => {
+                let (vertices, indices) = torus_knot();
+                let mesh = MeshletMesh::build(vertices, &indices);
+                let renderer = MeshletRenderer::new(
+                    device,
+                    &mesh,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    self.canvas_width,
+                    self.canvas_height,
+                );
+                if let Err(e) = &renderer {
+                    log::warn!("{}", e);
+                }
+                self.camera = Camera::framing([0.0; 3], 3.0);
+                self.render_state = RenderState::MeshShading(Box::new(MeshShadingState {
+                    mesh,
+                    renderer,
+                    settings: MeshletSettings::default(),
+                    visible: 0,
+                }));
+            }
+            id => {
+                if let Some(filter) = ImageFilter::from_example_id(id) {
+                    self.create_image_processing_state(device, queue, filter);
+                } else if let Some(algorithm) = Algorithm::from_example_id(id) {
+                    let mut state = AlgorithmState {
+                        runner: AlgorithmRunner::new(device),
+                        algorithm,
+                        comparison: None,
+                        error: None,
+                    };
+                    state.run(device, queue, self.algorithm_params);
+                    self.render_state = RenderState::Algorithms(Box::new(state));
+                } else if id == "bitonic_sort" {
+                    let mut state = SortState {
+                        sorter: BitonicSorter::new(device),
+                        comparison: None,
+                        snapshots: Vec::new(),
+                        snapshot_key_type: None,
+                        image: None,
+                        error: None,
+                    };
+                    state.run(device, queue, self.sort_params);
+                    self.render_state = RenderState::Sort(Box::new(state));
+                }
+            }
+        });
+        if gpu_errors.has_error(START_EXAMPLE) {
+            self.is_example_running = false;
+            self.render_state = RenderState::None;
+        }
+    }
+
     fn create_triangle_render_state(&mut self, device: &Device, queue: &Queue) {
         let shader_source = r#"
 struct VertexInput {
@@ -392,7 +921,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 alpha_to_coverage_enabled: false,
             },
             multiview_mask: None,
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         self.render_state = RenderState::Triangle(Box::new(TriangleState {
@@ -529,8 +1058,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         }
 
         let uniforms = Uniforms {
-            view_proj: identity_matrix(),
-            model: identity_matrix(),
+            view_proj: MAT4_IDENTITY,
+            model: MAT4_IDENTITY,
         };
 
         tracker.record(ApiCategory::Buffer, "create_buffer");
@@ -643,7 +1172,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 alpha_to_coverage_enabled: false,
             },
             multiview_mask: None,
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         self.render_state = RenderState::Cube(Box::new(CubeState {
@@ -912,7 +1441,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 alpha_to_coverage_enabled: false,
             },
             multiview_mask: None,
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         self.render_state = RenderState::Texture(Box::new(TextureState {
@@ -925,7 +1454,72 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         }));
     }
 
+    fn create_image_processing_state(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        filter: ImageFilter,
+    ) {
+        let mut processor = ImageProcessor::new(device);
+        self.processing_error = upload_processing_image(
+            self.processing_image.as_deref(),
+            device,
+            queue,
+            &mut processor,
+        );
+        self.processing_image_changed = false;
+        self.render_state = RenderState::ImageProcessing(Box::new(ImageProcessingState {
+            processor,
+            filter,
+            dirty: true,
+        }));
+    }
+
+    /// Use an encoded image, such as the file loaded in the texture panel,
+    /// as the input of the image processing examples
+    pub fn set_processing_image(&mut self, encoded: Option<&[u8]>) {
+        if self.processing_image.as_deref() != encoded {
+            self.processing_image = encoded.map(<[u8]>::to_vec);
+            self.processing_image_changed = true;
+        }
+    }
+
+    /// Re-run the image filter if its input or parameters changed
+    fn run_image_processing(&mut self, device: &Device, queue: &Queue) {
+        let RenderState::ImageProcessing(state) = &mut self.render_state else {
+            return;
+        };
+        if std::mem::take(&mut self.processing_image_changed) {
+            self.processing_error = upload_processing_image(
+                self.processing_image.as_deref(),
+                device,
+                queue,
+                &mut state.processor,
+            );
+            state.dirty = true;
+        }
+        if std::mem::take(&mut state.dirty) {
+            if let Err(e) =
+                state
+                    .processor
+                    .process(device, queue, state.filter, &self.filter_params)
+            {
+                self.processing_error = Some(e);
+            }
+        }
+    }
+
     fn render_current_example(&mut self, device: &Device, queue: &Queue) {
+        crate::cpu_scope!("Render example");
+        if let RenderState::ImageProcessing(_) = &self.render_state {
+            self.run_image_processing(device, queue);
+            return;
+        }
+        // Algorithms only run when asked to
+        if let RenderState::Algorithms(_) | RenderState::Sort(_) = &self.render_state {
+            return;
+        }
+
         // Update animation state
         // NOTE: Currently assumes 60fps with hardcoded 0.016s delta_time.
         // For variable frame rates, RenderingPanel would need to track last_frame_time
@@ -936,12 +1530,203 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         self.render_state.update(
             queue,
             0.016, // ~60fps
-            self.camera_distance,
-            self.camera_rotation_x,
-            self.camera_rotation_y,
+            &self.camera,
             aspect,
         );
 
+        if let RenderState::Instanced(state) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                state.cubes.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Bindless(state) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                state.grid.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Lod(scene) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                let lod_view = LodView {
+                    view_proj: self.camera.view_projection(aspect),
+                    eye: self.camera.eye(),
+                    fov_y: self.camera.fov_y,
+                    viewport_height: self.canvas_height,
+                };
+                scene.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    &lod_view,
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Grass(state) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                state.field.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Terrain(terrain) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                terrain.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Text(scene) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                scene.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Boids(flock) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                flock.render(device, queue, view, wgpu::Color { r, g, b, a });
+            }
+            return;
+        }
+
+        if let RenderState::Fluid(sim) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                sim.render(device, queue, view);
+            }
+            return;
+        }
+
+        if let RenderState::Water(state) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                state.scene.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Fog(state) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                state.scene.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::Shadow(scene) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                scene.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::TileStreaming(streamer) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                let [r, g, b, a] = self.clear_color.map(f64::from);
+                streamer.render(
+                    device,
+                    queue,
+                    view,
+                    self.camera.view_projection(aspect),
+                    wgpu::Color { r, g, b, a },
+                );
+            }
+            return;
+        }
+
+        if let RenderState::RayTracing(state) = &mut self.render_state {
+            if let Some(view) = &self.render_texture_view {
+                state
+                    .tracer
+                    .render(device, queue, view, (self.canvas_width, self.canvas_height));
+            }
+            return;
+        }
+
+        // Without ray queries the canvas is just cleared below
+        if let RenderState::HardwareRayTracing(state) = &mut self.render_state {
+            if let (Ok(scene), Some(view)) = (&mut state.scene, &self.render_texture_view) {
+                scene.render(device, queue, view);
+                return;
+            }
+        }
+
+        // Without mesh shaders the canvas is just cleared below
+        if let RenderState::MeshShading(state) = &mut self.render_state {
+            if let (Ok(renderer), Some(view)) = (&mut state.renderer, &self.render_texture_view) {
+                renderer.render(
+                    device,
+                    queue,
+                    view,
+                    (self.canvas_width, self.canvas_height),
+                    &state.settings,
+                );
+                return;
+            }
+        }
+
         if let Some(view) = &self.render_texture_view {
             // Track command encoder creation (only on first frame to avoid spam)
             let tracker = ApiCoverageTracker::global();
@@ -1029,13 +1814,30 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                         tracker.record(ApiCategory::RenderPass, "draw_indexed");
                         render_pass.draw_indexed(0..6, 0, 0..1);
                     }
-                    RenderState::None => {}
+                    RenderState::Instanced(_)
+                    | RenderState::Bindless(_)
+                    | RenderState::Lod(_)
+                    | RenderState::Grass(_)
+                    | RenderState::Terrain(_)
+                    | RenderState::Text(_)
+                    | RenderState::Boids(_)
+                    | RenderState::Fluid(_)
+                    | RenderState::Water(_)
+                    | RenderState::Fog(_)
+                    | RenderState::Shadow(_)
+                    | RenderState::TileStreaming(_)
+                    | RenderState::RayTracing(_)
+                    | RenderState::HardwareRayTracing(_)
+                    | RenderState::MeshShading(_)
+                    | RenderState::ImageProcessing(_)
+                    | RenderState::Algorithms(_)
+                    | RenderState::Sort(_)
+                    | RenderState::None => {}
                 }
             }
 
             tracker.record(ApiCategory::CommandEncoder, "finish");
-            tracker.record(ApiCategory::Queue, "submit");
-            queue.submit(std::iter::once(encoder.finish()));
+            upload_belt::submit(queue, std::iter::once(encoder.finish()));
         }
     }
 
@@ -1206,7 +2008,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             if let Some(0) = self.selected_example {
                 // Auto-run the first example (triangle)
                 self.is_example_running = true;
-                self.create_triangle_render_state(device, queue);
+                self.start_example(device, queue, "triangle");
             }
         }
 
@@ -1228,10 +2030,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
             if self.show_shader_editor {
                 // Show the shader editor
-                // TODO(shader_editor): Pass device when available for compilation support
-                // Currently compilation is disabled without a device
-                // See issue: Need to make device available to RenderingPanel
-                self.shader_editor.ui(ui, None);
+                self.shader_editor
+                    .ui_with_preview(ui, device, queue, renderer);
             } else {
                 // Show the example gallery (existing code)
                 self.render_example_gallery(ui, device, queue, renderer);
@@ -1263,36 +2063,66 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
                 // Display the rendered texture prominently
                 #[cfg(not(target_arch = "wasm32"))]
-                if let Some(texture_id) = self.register_texture(device, renderer) {
+                if let RenderState::ImageProcessing(state) = &mut self.render_state {
+                    let max_size = egui::vec2(self.canvas_width as f32, self.canvas_height as f32);
+                    show_split_preview(
+                        ui,
+                        device,
+                        renderer,
+                        state,
+                        &mut self.split_position,
+                        max_size,
+                    );
+                } else if let RenderState::Algorithms(state) = &self.render_state {
+                    show_algorithm_results(ui, state);
+                } else if let RenderState::Sort(state) = &mut self.render_state {
+                    show_sort_results(ui, state);
+                } else if let Some(texture_id) = self.register_texture(device, renderer) {
                     let size = egui::vec2(self.canvas_width as f32, self.canvas_height as f32);
 
-                    // Create an interactive canvas for mouse control
-                    let response = ui.add(
-                        egui::Image::new(egui::load::SizedTexture::new(texture_id, size))
-                            .sense(egui::Sense::click_and_drag()),
+                    // Create an interactive canvas for mouse control, with the
+                    // backdrop showing through a transparent clear color
+                    let (rect, response) =
+                        ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+                    theme::current().paint_backdrop_pattern(
+                        ui.painter(),
+                        rect,
+                        self.preview_backdrop,
                     );
+                    egui::Image::new(egui::load::SizedTexture::new(texture_id, size))
+                        .paint_at(ui, rect);
 
                     // Handle mouse interaction for 3D camera control
-                    if example_id == "cube" {
-                        if response.dragged() {
-                            let delta = response.drag_delta();
-                            self.camera_rotation_y += delta.x * 0.01;
-                            self.camera_rotation_x -= delta.y * 0.01;
-                            // Clamp rotation_x to avoid gimbal lock
-                            self.camera_rotation_x = self
-                                .camera_rotation_x
-                                .clamp(-std::f32::consts::PI / 2.0, std::f32::consts::PI / 2.0);
+                    if uses_camera(example_id) {
+                        self.camera.handle_input(ui, &response);
+                    }
+                    if let RenderState::Boids(flock) = &mut self.render_state {
+                        if let Some(pos) = response
+                            .interact_pointer_pos()
+                            .filter(|_| response.clicked())
+                        {
+                            let at = pos - response.rect.min;
+                            let uv = [at.x / response.rect.width(), at.y / response.rect.height()];
+                            flock.add_obstacle(canvas_to_world(uv, flock.aspect()));
                         }
-
-                        // Mouse wheel for zoom - only when hovering over the image
-                        if response.hovered() {
-                            let scroll_delta = ui.input(|i| i.smooth_scroll_delta.y);
-                            if scroll_delta.abs() > 0.1 {
-                                self.camera_distance -= scroll_delta * 0.01;
-                                self.camera_distance = self.camera_distance.clamp(1.0, 10.0);
-                            }
+                    }
+                    if let RenderState::Fluid(sim) = &mut self.render_state {
+                        if let Some(pos) = response
+                            .interact_pointer_pos()
+                            .filter(|_| response.dragged())
+                        {
+                            let (at, size) = (pos - response.rect.min, response.rect.size());
+                            let delta = response.drag_delta();
+                            sim.drag(
+                                [at.x / size.x, at.y / size.y],
+                                [delta.x / size.x, delta.y / size.y],
+                            );
                         }
                     }
+                    if let Some(texture) = &self.render_texture {
+                        self.pixel_picker
+                            .image_hover_ui(ui, &response, device, queue, texture);
+                    }
 
                     ui.add_space(5.0);
                     ui.horizontal(|ui| {
@@ -1300,9 +2130,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                             egui::RichText::new("✓ Rendering with WebGPU")
                                 .color(egui::Color32::from_rgb(100, 255, 100)),
                         );
-                        if example_id == "cube" {
+                        if uses_camera(example_id) {
                             ui.label(
-                                egui::RichText::new("💡 Drag to rotate, scroll to zoom")
+                                egui::RichText::new(self.camera.help_text())
                                     .color(egui::Color32::GRAY)
                                     .italics(),
                             );
@@ -1344,6 +2174,24 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             {
                 self.category_filter = Some(ExampleCategory::Compute);
             }
+            if ui
+                .selectable_label(
+                    self.category_filter == Some(ExampleCategory::ImageProcessing),
+                    "Image Processing",
+                )
+                .clicked()
+            {
+                self.category_filter = Some(ExampleCategory::ImageProcessing);
+            }
+            if ui
+                .selectable_label(
+                    self.category_filter == Some(ExampleCategory::Algorithms),
+                    "Algorithms",
+                )
+                .clicked()
+            {
+                self.category_filter = Some(ExampleCategory::Algorithms);
+            }
         });
 
         ui.add_space(10.0);
@@ -1371,6 +2219,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     let category_icon = match example.category {
                         ExampleCategory::Rendering => "🎨",
                         ExampleCategory::Compute => "🧮",
+                        ExampleCategory::ImageProcessing => "🖼",
+                        ExampleCategory::Algorithms => "∑",
                     };
 
                     if ui
@@ -1484,12 +2334,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
                     ui.add_space(10.0);
 
-                    // Run button (only for rendering examples with implementations)
-                    let has_implementation = example_id == "triangle"
-                        || example_id == "cube"
-                        || example_id == "texture_mapping";
-
-                    if example_category == ExampleCategory::Rendering && has_implementation {
+                    // Run button (only for rendering and image processing examples with implementations)
+                    if has_interactive_demo(&self.examples[idx]) {
                         if ui
                             .button(if self.is_example_running {
                                 "⏹ Stop Example"
@@ -1503,16 +2349,10 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                                 self.render_state = RenderState::None;
                             } else {
                                 self.is_example_running = true;
-                                // Create render state based on example
-                                if example_id == "triangle" {
-                                    self.create_triangle_render_state(device, queue);
-                                } else if example_id == "cube" {
-                                    self.create_cube_render_state(device, queue);
-                                } else if example_id == "texture_mapping" {
-                                    self.create_texture_mapping_render_state(device, queue);
-                                }
+                                self.start_example(device, queue, example_id);
                             }
                         }
+                        self.gpu_errors.ui(ui, START_EXAMPLE);
                     } else if example_category == ExampleCategory::Rendering {
                         // Show message for unimplemented rendering examples
                         ui.horizontal(|ui| {
@@ -1542,74 +2382,145 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                         );
                     }
 
-                    // Canvas controls (only if example is running)
-                    if self.is_example_running && example_category == ExampleCategory::Rendering {
+                    if self.is_example_running
+                        && example_category == ExampleCategory::ImageProcessing
+                    {
                         ui.add_space(10.0);
+                        self.image_processing_controls(ui);
+                    }
 
-                        ui.collapsing("⚙️ Canvas Controls", |ui| {
-                            ui.horizontal(|ui| {
-                                ui.label("Canvas Size:");
-                                let mut width = self.canvas_width;
-                                let mut height = self.canvas_height;
+                    if self.is_example_running
+                        && example_category == ExampleCategory::Algorithms
+                    {
+                        ui.add_space(10.0);
+                        self.algorithm_controls(ui, device, queue);
+                        self.sort_controls(ui, device, queue);
+                    }
 
-                                ui.add(
-                                    egui::DragValue::new(&mut width)
-                                        .prefix("W: ")
-                                        .range(64..=2048),
-                                );
-                                ui.add(
-                                    egui::DragValue::new(&mut height)
-                                        .prefix("H: ")
-                                        .range(64..=2048),
-                                );
+                    if self.is_example_running && example_id == "instanced_cubes" {
+                        ui.add_space(10.0);
+                        self.instancing_controls(ui, queue);
+                    }
 
-                                if ui.button("Apply").clicked() {
-                                    self.resize_canvas(device, width, height);
-                                    // Restart the current example with new size
-                                    if example_id == "triangle" {
-                                        self.create_triangle_render_state(device, queue);
-                                    } else if example_id == "cube" {
-                                        self.create_cube_render_state(device, queue);
-                                    }
-                                }
-                            });
+                    if self.is_example_running && example_id == "bindless_materials" {
+                        ui.add_space(10.0);
+                        self.bindless_controls(ui, device, queue);
+                    }
 
-                            ui.horizontal(|ui| {
-                                ui.label("Clear Color:");
-                                ui.color_edit_button_rgba_unmultiplied(&mut self.clear_color);
-                            });
+                    if self.is_example_running && example_id == "lod_meshes" {
+                        ui.add_space(10.0);
+                        self.lod_controls(ui);
+                    }
 
-                            if ui.button("📷 Capture Screenshot").clicked() {
-                                self.capture_screenshot(device, queue);
-                            }
+                    if self.is_example_running && example_id == "compute_grass" {
+                        ui.add_space(10.0);
+                        self.grass_controls(ui);
+                    }
 
-                            // Camera controls for 3D examples
-                            if example_id == "cube" {
-                                ui.separator();
-                                ui.label("Camera Controls:");
-                                ui.add(
-                                    egui::Slider::new(&mut self.camera_distance, 1.0..=10.0)
-                                        .text("Distance"),
-                                );
-                                ui.add(
-                                    egui::Slider::new(
-                                        &mut self.camera_rotation_x,
-                                        -std::f32::consts::FRAC_PI_2..=std::f32::consts::FRAC_PI_2,
-                                    )
-                                    .text("Rotation X (up/down)"),
-                                );
-                                ui.add(
-                                    egui::Slider::new(
-                                        &mut self.camera_rotation_y,
-                                        -std::f32::consts::PI..=std::f32::consts::PI,
-                                    )
-                                    .text("Rotation Y (left/right)"),
+                    if self.is_example_running && example_id == "terrain" {
+                        ui.add_space(10.0);
+                        self.terrain_controls(ui);
+                    }
+
+                    if self.is_example_running && example_id == "text_rendering" {
+                        ui.add_space(10.0);
+                        self.text_controls(ui);
+                    }
+
+                    if self.is_example_running && example_id == "boids" {
+                        ui.add_space(10.0);
+                        self.boids_controls(ui, queue);
+                    }
+
+                    if self.is_example_running && example_id == "fluid_simulation" {
+                        ui.add_space(10.0);
+                        self.fluid_controls(ui, device, queue);
+                    }
+
+                    if self.is_example_running && example_id == "water" {
+                        ui.add_space(10.0);
+                        self.water_controls(ui);
+                    }
+
+                    if self.is_example_running && example_id == "volumetric_fog" {
+                        ui.add_space(10.0);
+                        self.fog_controls(ui);
+                    }
+
+                    if self.is_example_running && example_id == "shadow_bias" {
+                        ui.add_space(10.0);
+                        self.shadow_controls(ui);
+                    }
+
+                    if self.is_example_running && example_id == "tile_streaming" {
+                        ui.add_space(10.0);
+                        self.streaming_controls(ui, device, queue, renderer);
+                    }
+
+                    if self.is_example_running && example_id == "ray_tracing" {
+                        ui.add_space(10.0);
+                        self.ray_tracer_controls(ui, device);
+                    }
+
+                    if self.is_example_running && example_id == "hardware_ray_tracing" {
+                        ui.add_space(10.0);
+                        self.ray_query_controls(ui);
+                    }
+
+                    if self.is_example_running && example_id == "mesh_shading" {
+                        ui.add_space(10.0);
+                        self.mesh_shading_controls(ui);
+                    }
+
+                    // Canvas controls (only if example is running)
+                    if self.is_example_running && example_category == ExampleCategory::Rendering {
+                        ui.add_space(10.0);
+
+                        ui.collapsing("⚙️ Canvas Controls", |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("Canvas Size:");
+                                let mut width = self.canvas_width;
+                                let mut height = self.canvas_height;
+
+                                ui.add(
+                                    egui::DragValue::new(&mut width)
+                                        .prefix("W: ")
+                                        .range(64..=2048),
+                                );
+                                ui.add(
+                                    egui::DragValue::new(&mut height)
+                                        .prefix("H: ")
+                                        .range(64..=2048),
                                 );
-                                if ui.button("Reset Camera").clicked() {
-                                    self.camera_distance = 3.0;
-                                    self.camera_rotation_x = 0.0;
-                                    self.camera_rotation_y = 0.0;
+
+                                if ui.button("Apply").clicked() {
+                                    self.resize_canvas(device, width, height);
+                                    // Restart the current example with new size
+                                    if example_id == "triangle" || example_id == "cube" {
+                                        self.start_example(device, queue, example_id);
+                                    }
                                 }
+                            });
+
+                            ui.horizontal(|ui| {
+                                ui.label("Clear Color:");
+                                ui.color_edit_button_rgba_unmultiplied(&mut self.clear_color);
+                                ui.label("Backdrop:");
+                                theme::backdrop_selector(
+                                    ui,
+                                    "preview_backdrop",
+                                    &mut self.preview_backdrop,
+                                );
+                            });
+
+                            if ui.button("📷 Capture Screenshot").clicked() {
+                                self.capture_screenshot(device, queue);
+                            }
+
+                            // Camera controls for 3D examples
+                            if uses_camera(example_id) {
+                                ui.separator();
+                                self.camera.ui(ui);
                             }
                         });
                     }
@@ -1633,18 +2544,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                         ui.add_space(5.0);
                         ui.separator();
                         ui.label(egui::RichText::new("Source Code:").strong());
-
-                        egui::ScrollArea::vertical()
-                            .max_height(300.0)
-                            .show(ui, |ui| {
-                                let mut source_code = example_source_code.to_string();
-                                ui.add(
-                                    egui::TextEdit::multiline(&mut source_code)
-                                        .code_editor()
-                                        .desired_width(f32::INFINITY)
-                                        .interactive(false),
-                                );
-                            });
+                        self.source_viewer.ui(ui, &self.examples[idx]);
                     }
 
                     // Copy source code button
@@ -1662,7 +2562,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     ui.separator();
                     ui.label(egui::RichText::new("💾 Export to Standalone Project").strong());
                     ui.label(
-                        "Generate a complete Cargo project that you can build and run separately.",
+                        "Generate a complete project that you can build and run separately.",
                     );
 
                     ui.horizontal(|ui| {
@@ -1670,6 +2570,44 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                         ui.text_edit_singleline(&mut self.export_project_name);
                     });
 
+                    ui.horizontal(|ui| {
+                        use crate::code_generator::ExportTarget;
+
+                        ui.label("Target:");
+                        ui.selectable_value(
+                            &mut self.export_target,
+                            ExportTarget::Native,
+                            "Native",
+                        );
+                        ui.selectable_value(
+                            &mut self.export_target,
+                            ExportTarget::Web,
+                            "Web (WASM)",
+                        )
+                        .on_hover_text(
+                            "A wasm-pack project with an index.html that renders into a canvas \
+                             in the browser, and still runs natively",
+                        );
+                        ui.selectable_value(
+                            &mut self.export_target,
+                            ExportTarget::Cpp,
+                            "C++ (Dawn)",
+                        )
+                        .on_hover_text(
+                            "A CMake project using the webgpu.h API, built against Dawn and \
+                             rendering into a GLFW window",
+                        );
+                        ui.selectable_value(
+                            &mut self.export_target,
+                            ExportTarget::Python,
+                            "Python (wgpu-py)",
+                        )
+                        .on_hover_text(
+                            "A Python script using wgpu-py that renders into a rendercanvas \
+                             window; compute projects print the buffer instead",
+                        );
+                    });
+
                     if ui.button("📦 Export Project").clicked() {
                         self.export_to_standalone_project(example_id, example_source_code);
                     }
@@ -1700,6 +2638,1152 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         );
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn image_processing_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::ImageProcessing(state) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Filter Controls", |ui| {
+            let previous = self.filter_params;
+            let params = &mut self.filter_params;
+            match state.filter {
+                ImageFilter::GaussianBlur => {
+                    ui.add(
+                        egui::Slider::new(&mut params.blur_radius, 0..=MAX_BLUR_RADIUS)
+                            .text("Radius (px)"),
+                    );
+                    ui.add(egui::Slider::new(&mut params.blur_sigma, 0.5..=16.0).text("Sigma"));
+                }
+                ImageFilter::SobelEdges => {
+                    ui.add(
+                        egui::Slider::new(&mut params.edge_strength, 0.1..=8.0)
+                            .logarithmic(true)
+                            .text("Strength"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut params.edge_underlay, 0.0..=1.0)
+                            .text("Original underneath"),
+                    );
+                }
+                ImageFilter::HistogramEqualization => {
+                    ui.add(
+                        egui::Slider::new(&mut params.equalize_amount, 0.0..=1.0).text("Amount"),
+                    );
+                }
+            }
+            ui.add(
+                egui::Slider::new(&mut self.split_position, 0.0..=1.0).text("Before / after split"),
+            );
+            if ui.button("Reset Parameters").clicked() {
+                self.filter_params = FilterParams::default();
+            }
+            if self.filter_params != previous {
+                state.dirty = true;
+            }
+
+            ui.separator();
+            if let Some((width, height)) = state.processor.size() {
+                let input = if self.processing_image.is_some() && self.processing_error.is_none() {
+                    "loaded texture"
+                } else {
+                    "test pattern"
+                };
+                ui.label(format!("Input: {} ({}x{})", input, width, height));
+            }
+            if self.processing_image.is_none() {
+                ui.label(
+                    egui::RichText::new("💡 Load an image in the Textures panel to process it")
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            }
+            if let Some(error) = &self.processing_error {
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+            }
+        });
+    }
+
+    fn algorithm_controls(&mut self, ui: &mut egui::Ui, device: &Device, queue: &Queue) {
+        let RenderState::Algorithms(state) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Algorithm Controls", |ui| {
+            let params = &mut self.algorithm_params;
+            ui.horizontal(|ui| {
+                ui.label("Operator:");
+                for op in ReduceOp::all() {
+                    ui.selectable_value(&mut params.op, *op, op.label());
+                }
+            });
+            ui.add(
+                egui::Slider::new(&mut params.element_count, MIN_ELEMENTS..=MAX_ELEMENTS)
+                    .logarithmic(true)
+                    .text("Elements"),
+            );
+            if ui.button("▶ Run Comparison").clicked() {
+                state.run(device, queue, *params);
+            }
+            if ui.button("Reset Parameters").clicked() {
+                *params = AlgorithmParams::default();
+            }
+            ui.label(
+                egui::RichText::new(
+                    "Times are wall-clock from submit until the GPU is idle, so small inputs \
+                     mostly measure submission overhead",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            if let Some(error) = &state.error {
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+            }
+        });
+    }
+
+    fn sort_controls(&mut self, ui: &mut egui::Ui, device: &Device, queue: &Queue) {
+        let RenderState::Sort(state) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Sort Controls", |ui| {
+            let params = &mut self.sort_params;
+            ui.horizontal(|ui| {
+                ui.label("Keys:");
+                for key_type in KeyType::all() {
+                    ui.selectable_value(&mut params.key_type, *key_type, key_type.label());
+                }
+            });
+            ui.add(
+                egui::Slider::new(&mut params.key_count, MIN_KEYS..=MAX_KEYS)
+                    .logarithmic(true)
+                    .text("Key count"),
+            );
+            if ui.button("▶ Run Comparison").clicked() {
+                state.run(device, queue, *params);
+            }
+            if ui.button("Reset Parameters").clicked() {
+                *params = SortParams::default();
+            }
+            ui.label(
+                egui::RichText::new(
+                    "Key counts that are not a power of two are padded, so they take as long \
+                     as the next power of two",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            if let Some(error) = &state.error {
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+            }
+        });
+    }
+
+    fn bindless_controls(&mut self, ui: &mut egui::Ui, device: &Device, queue: &Queue) {
+        let RenderState::Bindless(state) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Bindless Controls", |ui| {
+            let grid = &mut state.grid;
+            let (color, icon) = match grid.path() {
+                MaterialPath::BindingArray => (egui::Color32::from_rgb(100, 200, 100), "✅"),
+                MaterialPath::Atlas => (egui::Color32::from_rgb(200, 150, 50), "⚠"),
+            };
+            ui.colored_label(
+                color,
+                format!("{} Active path: {}", icon, grid.path().label()),
+            );
+            if let Some(reason) = grid.fallback() {
+                ui.label(format!("Why: {}", reason));
+            }
+
+            let support = binding_array_support(device.features(), &device.limits());
+            let mut path = grid.path();
+            ui.horizontal(|ui| {
+                ui.label("Path:");
+                ui.add_enabled_ui(support.is_ok(), |ui| {
+                    ui.selectable_value(
+                        &mut path,
+                        MaterialPath::BindingArray,
+                        MaterialPath::BindingArray.label(),
+                    );
+                });
+                ui.selectable_value(&mut path, MaterialPath::Atlas, MaterialPath::Atlas.label());
+            });
+            if path != grid.path() {
+                *grid = MaterialGrid::new(
+                    device,
+                    queue,
+                    wgpu::TextureFormat::Bgra8UnormSrgb,
+                    path == MaterialPath::BindingArray,
+                );
+            }
+            ui.label(
+                egui::RichText::new(
+                    "A binding array lets every fragment pick its own texture by index. The \
+                     atlas needs only core WebGPU but packs every material into one texture, \
+                     so all materials share its size and format and need gutters against \
+                     filtering across cells.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            egui::Grid::new("bindless_stats").show(ui, |ui| {
+                ui.label("Materials");
+                ui.label(MATERIAL_COUNT.to_string());
+                ui.end_row();
+                ui.label("Textures in the bind group");
+                ui.label(grid.textures_bound().to_string());
+                ui.end_row();
+                ui.label("Bind groups per frame");
+                ui.label("1");
+                ui.end_row();
+                ui.label("Draw calls per frame");
+                ui.label(format!("1 ({} cubes)", grid.instance_count()));
+                ui.end_row();
+            });
+        });
+    }
+
+    fn instancing_controls(&mut self, ui: &mut egui::Ui, queue: &Queue) {
+        let RenderState::Instanced(state) = &mut self.render_state else {
+            return;
+        };
+        let cubes = &mut state.cubes;
+        let camera = &mut self.camera;
+        ui.collapsing("⚙️ Instancing Controls", |ui| {
+            let mut count = cubes.instance_count();
+            if ui
+                .add(
+                    egui::Slider::new(&mut count, 1..=MAX_INSTANCES)
+                        .logarithmic(true)
+                        .text("Cubes"),
+                )
+                .changed()
+            {
+                cubes.set_instance_count(queue, count);
+                // Refit the camera to the grown or shrunk grid
+                camera.frame([0.0; 3], grid_radius(count));
+            }
+
+            let mut path = cubes.path();
+            ui.horizontal(|ui| {
+                ui.label("Draw path:");
+                for option in [DrawPath::Instanced, DrawPath::PerObject] {
+                    ui.selectable_value(&mut path, option, option.label());
+                }
+            });
+            cubes.set_path(path);
+            ui.label(format!("Draw calls per frame: {}", cubes.draw_calls()));
+
+            ui.separator();
+            ui.label(egui::RichText::new("CPU frame time (record + submit)").strong());
+            egui::Grid::new("instancing_frame_times").show(ui, |ui| {
+                for option in [DrawPath::Instanced, DrawPath::PerObject] {
+                    ui.label(option.label());
+                    match cubes.frame_time(option) {
+                        Some(time) => ui.label(format!("{:.3} ms", time.as_secs_f64() * 1000.0)),
+                        None => ui.label(
+                            egui::RichText::new("switch to this path to measure")
+                                .color(egui::Color32::GRAY),
+                        ),
+                    };
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn lod_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::Lod(scene) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ LOD Controls", |ui| {
+            let mut path = scene.path();
+            ui.horizontal(|ui| {
+                ui.label("Selection:");
+                for option in [SelectionPath::Cpu, SelectionPath::Gpu] {
+                    ui.selectable_value(&mut path, option, option.label());
+                }
+            });
+            scene.set_path(path);
+
+            let settings = &mut scene.settings;
+            ui.add(
+                egui::Slider::new(&mut settings.tolerance, 0.1..=20.0)
+                    .logarithmic(true)
+                    .text("Error tolerance (px)"),
+            );
+            ui.horizontal(|ui| {
+                ui.label("Mesh:");
+                ui.selectable_value(&mut settings.forced_lod, None, "By error");
+                for lod in 0..LOD_COUNT {
+                    ui.selectable_value(&mut settings.forced_lod, Some(lod), format!("LOD {lod}"));
+                }
+            });
+            ui.checkbox(&mut settings.cull, "Frustum culling");
+            ui.checkbox(&mut scene.show_lod, "Tint by LOD");
+
+            ui.separator();
+            let Some(counts) = scene.counts() else {
+                ui.label(
+                    egui::RichText::new("Waiting for the GPU counts…").color(egui::Color32::GRAY),
+                );
+                return;
+            };
+            let meshes = scene.meshes();
+            let triangles = counts.triangles(meshes);
+            egui::Grid::new("lod_counts").show(ui, |ui| {
+                ui.label(egui::RichText::new("Mesh").strong());
+                ui.label(egui::RichText::new("Objects").strong());
+                ui.label(egui::RichText::new("Triangles").strong());
+                ui.end_row();
+                for lod in 0..LOD_COUNT {
+                    ui.label(format!("LOD {} ({} tris)", lod, meshes[lod].triangles()));
+                    ui.label(counts.instances[lod].to_string());
+                    ui.label(triangles[lod].to_string());
+                    ui.end_row();
+                }
+                ui.label("Total");
+                ui.label(counts.instances.iter().sum::<u32>().to_string());
+                ui.label(triangles.iter().sum::<u64>().to_string());
+                ui.end_row();
+                ui.label("Culled");
+                ui.label(counts.culled.to_string());
+                ui.end_row();
+            });
+            // What the same objects would cost with the finest mesh only
+            let finest =
+                u64::from(counts.instances.iter().sum::<u32>()) * u64::from(meshes[0].triangles());
+            ui.label(
+                egui::RichText::new(format!("{} triangles with LOD 0 only", finest))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        });
+    }
+
+    fn grass_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::Grass(state) = &mut self.render_state else {
+            return;
+        };
+        let field = &mut state.field;
+        ui.collapsing("⚙️ Grass Controls", |ui| {
+            let mut count = field.blade_count();
+            if ui
+                .add(
+                    egui::Slider::new(&mut count, 1_000..=MAX_BLADES)
+                        .logarithmic(true)
+                        .text("Blades"),
+                )
+                .changed()
+            {
+                field.set_blade_count(count);
+            }
+            ui.add(egui::Slider::new(&mut field.wind_strength, 0.0..=1.0).text("Wind strength"));
+
+            ui.horizontal(|ui| {
+                ui.label("Edges:");
+                for option in [CoverageMode::AlphaToCoverage, CoverageMode::AlphaTest] {
+                    ui.selectable_value(&mut field.coverage, option, option.label());
+                }
+            });
+            ui.label(
+                egui::RichText::new(
+                    "The same choice as Alpha to Coverage in the render pipeline's \
+                     multisample state. Zoom in on the blade tips to compare the edges.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            egui::Grid::new("grass_stats").show(ui, |ui| {
+                ui.label("Triangles per frame");
+                ui.label(field.triangles().to_string());
+                ui.end_row();
+                ui.label("Generated per frame");
+                ui.label(format!(
+                    "{:.1} MiB",
+                    field.generated_bytes() as f64 / (1024.0 * 1024.0)
+                ));
+                ui.end_row();
+            });
+        });
+    }
+
+    fn terrain_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::Terrain(terrain) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Terrain Controls", |ui| {
+            let noise = &mut terrain.noise;
+            ui.add(egui::Slider::new(&mut noise.octaves, 1..=MAX_OCTAVES).text("Octaves"));
+            ui.add(
+                egui::Slider::new(&mut noise.frequency, 0.001..=0.02)
+                    .logarithmic(true)
+                    .text("Frequency"),
+            );
+            ui.add(egui::Slider::new(&mut noise.lacunarity, 1.5..=3.0).text("Lacunarity"));
+            ui.add(egui::Slider::new(&mut noise.gain, 0.2..=0.8).text("Gain"));
+            ui.add(egui::Slider::new(&mut noise.height, 0.0..=200.0).text("Height"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut noise.seed).prefix("Seed: "));
+                if ui.button("🎲 New seed").clicked() {
+                    noise.seed = noise.seed.wrapping_add(1);
+                }
+            });
+
+            ui.checkbox(&mut terrain.wireframe, "Wireframe");
+            ui.checkbox(&mut terrain.show_levels, "Tint levels of detail");
+            ui.label(
+                egui::RichText::new(
+                    "The levels follow the camera; fly close to the ground with the \
+                     wireframe on to watch the rings move and the cells grow with distance.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            egui::Grid::new("terrain_stats").show(ui, |ui| {
+                ui.label("Vertices per frame");
+                ui.label(vertices_per_frame().to_string());
+                ui.end_row();
+                ui.label("Triangles per frame");
+                ui.label(triangles_per_frame().to_string());
+                ui.end_row();
+                ui.label("Levels of detail");
+                ui.label(LEVELS.to_string());
+                ui.end_row();
+                ui.label("Heightmap");
+                ui.label(format!("{0}x{0} r32float", HEIGHTMAP_SIZE));
+                ui.end_row();
+            });
+        });
+    }
+
+    fn text_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::Text(scene) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Text Controls", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Label:");
+                ui.text_edit_singleline(&mut scene.label);
+            });
+            ui.add(egui::Slider::new(&mut scene.label_size, 8.0..=96.0).text("Label size (px)"));
+            ui.checkbox(&mut scene.show_corners, "Label the cube corners");
+            ui.label(
+                egui::RichText::new(
+                    "Every new character or size is rasterized into the atlas once; \
+                     type or change the size to watch the glyph count grow.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            if let Some(error) = scene.error() {
+                ui.colored_label(egui::Color32::RED, error.to_string());
+            }
+
+            ui.separator();
+            let renderer = scene.renderer();
+            egui::Grid::new("text_stats").show(ui, |ui| {
+                ui.label("Glyph quads");
+                ui.label(renderer.glyph_count().to_string());
+                ui.end_row();
+                ui.label("Glyphs in atlas");
+                ui.label(renderer.atlas_glyphs().to_string());
+                ui.end_row();
+                ui.label("Atlas used");
+                ui.label(format!("{:.1}%", renderer.atlas_usage() * 100.0));
+                ui.end_row();
+            });
+        });
+    }
+
+    fn boids_controls(&mut self, ui: &mut egui::Ui, queue: &Queue) {
+        let RenderState::Boids(flock) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Boids Controls", |ui| {
+            let mut count = flock.boid_count();
+            if ui
+                .add(
+                    egui::Slider::new(&mut count, 64..=MAX_BOIDS)
+                        .logarithmic(true)
+                        .text("Boids"),
+                )
+                .changed()
+            {
+                flock.set_boid_count(count);
+            }
+            let params = &mut flock.params;
+            ui.add(egui::Slider::new(&mut params.cohesion, 0.0..=10.0).text("Cohesion"));
+            ui.add(egui::Slider::new(&mut params.separation, 0.0..=10.0).text("Separation"));
+            ui.add(egui::Slider::new(&mut params.alignment, 0.0..=10.0).text("Alignment"));
+            ui.add(egui::Slider::new(&mut params.visual_range, 0.02..=0.5).text("Visual range"));
+            ui.add(
+                egui::Slider::new(&mut params.separation_distance, 0.005..=0.2)
+                    .text("Separation distance"),
+            );
+            ui.add(egui::Slider::new(&mut params.max_speed, 0.05..=2.0).text("Max speed"));
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut flock.paused, "Paused");
+                if ui.button("Reset Rules").clicked() {
+                    flock.params = Default::default();
+                }
+                if ui.button("Scatter Boids").clicked() {
+                    flock.reset(queue);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Obstacles: {}/{}",
+                    flock.obstacles().len(),
+                    MAX_OBSTACLES
+                ));
+                if ui.button("Clear Obstacles").clicked() {
+                    flock.clear_obstacles();
+                }
+            });
+            ui.label(
+                egui::RichText::new(
+                    "Click the preview to place an obstacle; past the limit the oldest one \
+                     is replaced.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            egui::Grid::new("boids_stats").show(ui, |ui| {
+                ui.label("Neighbour checks per frame");
+                ui.label(flock.neighbour_checks().to_string());
+                ui.end_row();
+            });
+        });
+    }
+
+    fn fluid_controls(&mut self, ui: &mut egui::Ui, device: &Device, queue: &Queue) {
+        let RenderState::Fluid(sim) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Fluid Controls", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Show:");
+                for option in FluidView::ALL {
+                    ui.selectable_value(&mut sim.view, option, option.label());
+                }
+            });
+            let params = &mut sim.params;
+            ui.add(
+                egui::Slider::new(&mut params.pressure_iterations, 1..=MAX_PRESSURE_ITERATIONS)
+                    .text("Pressure iterations"),
+            );
+            ui.add(
+                egui::Slider::new(&mut params.dye_dissipation, 0.0..=3.0).text("Dye dissipation"),
+            );
+            ui.add(
+                egui::Slider::new(&mut params.velocity_dissipation, 0.0..=3.0)
+                    .text("Velocity dissipation"),
+            );
+            ui.add(egui::Slider::new(&mut params.splat_radius, 0.005..=0.2).text("Splat radius"));
+            ui.add(egui::Slider::new(&mut params.splat_force, 0.1..=5.0).text("Splat force"));
+
+            ui.horizontal(|ui| {
+                if ui.button("Random Splats").clicked() {
+                    sim.random_splats(5);
+                }
+                if ui.button("Clear").clicked() {
+                    sim.reset(device, queue);
+                }
+                if ui.button("Reset Parameters").clicked() {
+                    sim.params = Default::default();
+                }
+            });
+            ui.label(
+                egui::RichText::new(
+                    "Drag in the preview to push the fluid. Fewer pressure iterations leave \
+                     divergence behind, and the fluid visibly compresses.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            let (width, height) = sim.size();
+            egui::Grid::new("fluid_stats").show(ui, |ui| {
+                ui.label("Grid");
+                ui.label(format!("{}x{}", width, height));
+                ui.end_row();
+                ui.label("Dispatches per frame");
+                ui.label(sim.dispatches_per_frame().to_string());
+                ui.end_row();
+            });
+        });
+    }
+
+    fn water_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::Water(state) = &mut self.render_state else {
+            return;
+        };
+        let scene = &mut state.scene;
+        ui.collapsing("⚙️ Water Controls", |ui| {
+            ui.add(egui::Slider::new(&mut scene.wave_strength, 0.0..=1.5).text("Wave strength"));
+
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Show:");
+                for option in WaterView::ALL {
+                    ui.selectable_value(&mut scene.view, option, option.label());
+                }
+            });
+            ui.label(
+                egui::RichText::new(
+                    "Orbit down to the surface to watch the Fresnel term trade the \
+                     refracted floor for the reflected sky.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            ui.checkbox(
+                &mut scene.half_resolution,
+                "Half-resolution reflection and refraction",
+            );
+            let (width, height) = scene.offscreen_size();
+            ui.label(
+                egui::RichText::new(format!("Offscreen passes: 2 x {}x{}", width, height))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        });
+    }
+
+    fn fog_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::Fog(state) = &mut self.render_state else {
+            return;
+        };
+        let scene = &mut state.scene;
+        ui.collapsing("⚙️ Fog Controls", |ui| {
+            ui.add(egui::Slider::new(&mut scene.density, 0.0..=1.5).text("Density"));
+            ui.add(egui::Slider::new(&mut scene.steps, 4..=128).text("Steps per ray"));
+            ui.add(egui::Slider::new(&mut scene.anisotropy, -0.9..=0.9).text("Anisotropy"));
+
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Jitter:");
+                for option in FogJitter::ALL {
+                    ui.selectable_value(&mut scene.jitter, option, option.label());
+                }
+            });
+            ui.checkbox(&mut scene.temporal, "Blend with the previous frame");
+            ui.label(
+                egui::RichText::new(
+                    "Lower the steps and turn the jitter off to see the fog band into \
+                     slices; blue noise with the temporal blend hides them.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Show:");
+                for option in FogView::ALL {
+                    ui.selectable_value(&mut scene.view, option, option.label());
+                }
+            });
+        });
+    }
+
+    fn shadow_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::Shadow(scene) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Shadow Controls", |ui| {
+            ui.label(egui::RichText::new("Depth bias of the shadow pipeline").strong());
+            let state = &mut scene.shadow_state;
+            let unit = constant_bias_unit(state.format);
+            ui.add(
+                egui::Slider::new(&mut state.depth_bias, 0..=1_000_000)
+                    .logarithmic(true)
+                    .text("depth_bias"),
+            )
+            .on_hover_text(format!(
+                "Constant bias in the smallest depth steps of {:?}; one step is about {:.2e} \
+                 units along the light",
+                state.format, unit
+            ));
+            ui.add(
+                egui::Slider::new(&mut state.depth_bias_slope_scale, 0.0..=16.0)
+                    .text("depth_bias_slope_scale"),
+            )
+            .on_hover_text("Multiple of how much the triangle's depth changes across a texel");
+            ui.add(
+                egui::Slider::new(&mut state.depth_bias_clamp, 0.0..=0.05).text("depth_bias_clamp"),
+            )
+            .on_hover_text(
+                "Largest total bias, in depth; 0 leaves it unclamped. Needs the \
+                 DEPTH_BIAS_CLAMP downlevel flag",
+            );
+            ui.label(
+                egui::RichText::new(format!(
+                    "Constant bias ≈ {:.3} units along the light",
+                    state.depth_bias as f32 * unit
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Presets:");
+                for preset in BIAS_PRESETS {
+                    if ui.button(preset.0).clicked() {
+                        scene.apply_preset(preset);
+                    }
+                }
+            });
+            scene.errors().ui(ui, SHADOW_PIPELINE);
+
+            ui.separator();
+            ui.add(
+                egui::Slider::new(&mut scene.light_elevation, 5.0..=85.0)
+                    .text("Light elevation (°)"),
+            );
+            ui.add(
+                egui::Slider::new(&mut scene.light_azimuth, -180.0..=180.0)
+                    .text("Light azimuth (°)"),
+            );
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Shadow map:");
+                for size in SHADOW_MAP_SIZES {
+                    ui.selectable_value(&mut scene.shadow_map_size, size, size.to_string());
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Format:");
+                for format in SHADOW_FORMATS {
+                    ui.selectable_value(
+                        &mut scene.shadow_state.format,
+                        format,
+                        format!("{:?}", format),
+                    );
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Cull in shadow pass:");
+                for (mode, label) in [
+                    (CullMode::None, "None"),
+                    (CullMode::Back, "Back faces"),
+                    (CullMode::Front, "Front faces"),
+                ] {
+                    ui.selectable_value(&mut scene.cull_mode, mode, label);
+                }
+            });
+            ui.checkbox(&mut scene.filtered, "Filter 2x2 texels when comparing");
+            ui.label(
+                egui::RichText::new(
+                    "Lower the light and the map size to widen the acne stripes. Raise the \
+                     bias until they go, and watch the wall's shadow come loose from its base \
+                     when it is too large.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Show:");
+                for option in ShadowView::ALL {
+                    ui.selectable_value(&mut scene.view, option, option.label());
+                }
+            });
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn streaming_controls(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &Device,
+        queue: &Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        let RenderState::TileStreaming(streamer) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Streaming Controls", |ui| {
+            let mut budget = streamer.upload_budget();
+            if ui
+                .add(egui::Slider::new(&mut budget, 1..=64).text("Tile uploads per frame"))
+                .changed()
+            {
+                streamer.set_upload_budget(budget);
+            }
+            let mut bias = streamer.mip_bias();
+            if ui
+                .add(egui::Slider::new(&mut bias, -2.0..=4.0).text("Mip bias"))
+                .changed()
+            {
+                streamer.set_mip_bias(bias);
+            }
+            let mut show_tiles = streamer.show_tiles();
+            if ui.checkbox(&mut show_tiles, "Tint tiles by mip").changed() {
+                streamer.set_show_tiles(show_tiles);
+            }
+            if ui.button("🗑 Flush atlas").clicked() {
+                streamer.flush(queue);
+            }
+
+            ui.separator();
+            let stats = streamer.stats();
+            let atlas = streamer.atlas();
+            egui::Grid::new("tile_streaming_stats").show(ui, |ui| {
+                ui.label("Resident tiles:");
+                ui.label(format!("{} / {}", atlas.len(), atlas.capacity()));
+                ui.end_row();
+                ui.label("Resident per mip:");
+                let per_mip = atlas.resident_per_mip();
+                ui.label(
+                    (0..MIP_COUNT as usize)
+                        .map(|mip| format!("{}: {}", mip, per_mip[mip]))
+                        .collect::<Vec<_>>()
+                        .join("  "),
+                );
+                ui.end_row();
+                ui.label("Requested / pending:");
+                ui.label(format!("{} / {}", stats.requested, stats.pending));
+                ui.end_row();
+                ui.label("Uploaded:");
+                ui.label(format!(
+                    "{} this frame, {} total",
+                    stats.uploaded_last_frame, stats.uploaded_total
+                ));
+                ui.end_row();
+                ui.label("Evicted:");
+                ui.label(stats.evicted_total.to_string());
+                ui.end_row();
+                ui.label("Feedback frames:");
+                ui.label(format!(
+                    "{} read back, {} skipped",
+                    stats.feedback_frames, stats.skipped_feedback
+                ));
+                ui.end_row();
+            });
+
+            ui.separator();
+            ui.label(egui::RichText::new("Atlas").strong());
+            let texture_id = streamer.atlas_texture_id(device, renderer);
+            ui.add(egui::Image::new(egui::load::SizedTexture::new(
+                texture_id,
+                egui::vec2(256.0, 256.0),
+            )));
+        });
+    }
+
+    fn ray_query_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::HardwareRayTracing(state) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Ray Query Controls", |ui| {
+            let scene = match &mut state.scene {
+                Ok(scene) => scene,
+                Err(error) => {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(200, 150, 50),
+                        format!("⚠ {}", error),
+                    );
+                    return;
+                }
+            };
+            let settings = &mut scene.settings;
+            ui.horizontal(|ui| {
+                ui.label("View:");
+                for view in RayQueryView::ALL {
+                    ui.selectable_value(&mut settings.view, view, view.label());
+                }
+            });
+            ui.checkbox(&mut settings.shadows, "Trace shadow rays");
+            ui.checkbox(&mut settings.animate, "Move the cubes (rebuilds the TLAS)");
+            ui.add(
+                egui::Slider::new(&mut settings.sun_elevation, 5.0..=90.0)
+                    .text("Sun elevation (°)"),
+            );
+            ui.label(
+                egui::RichText::new(
+                    "The BLASes are built once; moving instances only rebuilds the TLAS \
+                     over them, which is cheap enough to do every frame.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            let structures = scene.structures();
+            egui::Grid::new("ray_query_stats").show(ui, |ui| {
+                ui.label("BLASes");
+                ui.label(structures.meshes().len().to_string());
+                ui.end_row();
+                ui.label("TLAS instances");
+                ui.label(structures.instances().len().to_string());
+                ui.end_row();
+                ui.label("Triangles");
+                ui.label(structures.triangle_count().to_string());
+                ui.end_row();
+                ui.label("TLAS builds");
+                ui.label(scene.tlas_builds().to_string());
+                ui.end_row();
+            });
+        });
+    }
+
+    fn mesh_shading_controls(&mut self, ui: &mut egui::Ui) {
+        let RenderState::MeshShading(state) = &mut self.render_state else {
+            return;
+        };
+        ui.collapsing("⚙️ Mesh Shader Controls", |ui| {
+            if let Err(error) = &state.renderer {
+                ui.colored_label(
+                    egui::Color32::from_rgb(200, 150, 50),
+                    format!("⚠ {}", error),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Mesh shaders replace the vertex stage: a task shader decides how many \
+                         mesh shader workgroups to launch, and each of those writes vertices \
+                         and triangles straight to the rasterizer. The meshlets below are still \
+                         built and culled on the CPU to show what the task shader would skip.",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+                ui.separator();
+            }
+            let settings = &mut state.settings;
+            ui.checkbox(&mut settings.frustum_culling, "Frustum culling");
+            ui.checkbox(&mut settings.cone_culling, "Normal cone culling");
+            if state.renderer.is_ok() {
+                ui.checkbox(&mut settings.color_meshlets, "Color by meshlet");
+                ui.label(
+                    egui::RichText::new(
+                        "Each task workgroup tests 32 meshlets and launches one mesh workgroup \
+                         per meshlet that is in view and has a triangle facing the camera.",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            }
+
+            ui.separator();
+            let mesh = &state.mesh;
+            egui::Grid::new("mesh_shading_stats").show(ui, |ui| {
+                ui.label("Triangles");
+                ui.label(mesh.triangle_count().to_string());
+                ui.end_row();
+                ui.label("Meshlets");
+                ui.label(mesh.meshlets.len().to_string());
+                ui.end_row();
+                ui.label("Triangle slots used");
+                ui.label(format!("{:.0}%", mesh.fill_rate() * 100.0));
+                ui.end_row();
+                ui.label("Cone cullable");
+                ui.label(mesh.cone_cullable().to_string());
+                ui.end_row();
+                ui.label("Meshlets drawn");
+                ui.label(format!(
+                    "{} ({:.0}% culled)",
+                    state.visible,
+                    100.0 * (1.0 - state.visible as f32 / mesh.meshlets.len().max(1) as f32)
+                ));
+                ui.end_row();
+                if let Ok(renderer) = &state.renderer {
+                    ui.label("Task workgroups");
+                    ui.label(renderer.task_groups().to_string());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn ray_tracer_controls(&mut self, ui: &mut egui::Ui, device: &Device) {
+        let RenderState::RayTracing(state) = &mut self.render_state else {
+            return;
+        };
+        let mut loaded = false;
+        ui.collapsing("⚙️ Ray Tracer Controls", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Model:");
+                ui.text_edit_singleline(&mut state.model);
+                if ui.button("📂 Load").clicked() {
+                    state.error = state.load(device).err();
+                    loaded = state.error.is_none();
+                }
+            });
+            if let Some(error) = &state.error {
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+            }
+
+            let settings = &mut state.tracer.settings;
+            ui.add(egui::Slider::new(&mut settings.max_bounces, 0..=MAX_BOUNCES).text("Bounces"));
+            ui.add(
+                egui::Slider::new(&mut settings.samples_per_frame, 1..=16)
+                    .text("Samples per frame"),
+            );
+            ui.add(
+                egui::Slider::new(&mut settings.max_samples, 16..=8192)
+                    .logarithmic(true)
+                    .text("Max samples"),
+            );
+            ui.add(
+                egui::Slider::new(&mut settings.sun_azimuth, -180.0..=180.0)
+                    .text("Sun azimuth (°)"),
+            );
+            ui.add(
+                egui::Slider::new(&mut settings.sun_elevation, 1.0..=90.0)
+                    .text("Sun elevation (°)"),
+            );
+            ui.add(
+                egui::Slider::new(&mut settings.emission_strength, 0.0..=20.0)
+                    .text("Emission strength"),
+            );
+            ui.checkbox(&mut settings.show_bvh, "Show BVH traversal cost");
+            if ui.button("🔄 Restart accumulation").clicked() {
+                state.tracer.restart();
+            }
+            ui.label(
+                egui::RichText::new(
+                    "Every frame adds samples to a running mean; moving the camera or \
+                     changing a setting starts over from a noisy first sample.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+
+            ui.separator();
+            let tracer = &state.tracer;
+            egui::Grid::new("ray_tracer_stats").show(ui, |ui| {
+                ui.label("Samples per pixel");
+                ui.label(if tracer.is_converged() {
+                    format!("{} (converged)", tracer.sample_count())
+                } else {
+                    format!(
+                        "{} / {}",
+                        tracer.sample_count(),
+                        tracer.settings.max_samples
+                    )
+                });
+                ui.end_row();
+                ui.label("Triangles");
+                ui.label(tracer.mesh().triangles.len().to_string());
+                ui.end_row();
+                ui.label("BVH nodes");
+                ui.label(tracer.bvh().nodes.len().to_string());
+                ui.end_row();
+                ui.label("BVH leaves");
+                ui.label(tracer.bvh().leaf_count().to_string());
+                ui.end_row();
+                ui.label("BVH depth");
+                ui.label(tracer.bvh().depth().to_string());
+                ui.end_row();
+            });
+        });
+
+        if loaded {
+            self.camera = state.framing();
+        }
+    }
+
+    /// Offer a virtual device as a lint target in the shader editor
+    pub fn set_lint_device(&mut self, device: Option<&crate::capability_snapshot::VirtualDevice>) {
+        self.shader_editor.set_lint_device(device);
+    }
+
+    /// Restart shader hot reload after the assets directory changed
+    pub fn restart_shader_watcher(&mut self) {
+        self.shader_editor.restart_watcher();
+    }
+
+    /// Use the clear color and backdrop of a new theme for the preview
+    pub fn apply_theme(&mut self, palette: &ThemePalette) {
+        self.clear_color = palette.preview_clear;
+        self.preview_backdrop = palette.backdrop;
+    }
+
+    /// Compile the shader editor's source and switch to the editor
+    pub fn compile_shader(&mut self, device: &Device) {
+        self.show_shader_editor = true;
+        self.shader_editor.compile(device);
+    }
+
+    /// Show `code` in the shader editor, labeled `label`
+    pub fn open_shader(&mut self, label: &str, code: String) {
+        self.show_shader_editor = true;
+        self.shader_editor.open_source(label, code);
+    }
+
+    /// Run the selected example's preview, or stop it if it is running
+    pub fn toggle_preview(&mut self, device: &Device, queue: &Queue) {
+        if self.is_example_running {
+            self.is_example_running = false;
+            self.render_state = RenderState::None;
+        } else if let Some(idx) = self.selected_example {
+            self.show_shader_editor = false;
+            self.is_example_running = true;
+            self.start_example(device, queue, self.examples[idx].id);
+            // Examples without an interactive demo have nothing to preview
+            if matches!(self.render_state, RenderState::None) {
+                self.is_example_running = false;
+            }
+        }
+    }
+
+    /// Select the example with the given id and start its preview
+    ///
+    /// Examples without a preview are only selected, so their source and
+    /// notes show. Returns false if there is no such example.
+    pub fn load_example(&mut self, example_id: &str, device: &Device, queue: &Queue) -> bool {
+        let Some(idx) = self.examples.iter().position(|e| e.id == example_id) else {
+            return false;
+        };
+        self.selected_example = Some(idx);
+        self.show_source_code = false;
+        self.show_shader_editor = false;
+        self.is_example_running = false;
+        self.render_state = RenderState::None;
+        if has_interactive_demo(&self.examples[idx]) {
+            self.is_example_running = true;
+            self.start_example(device, queue, example_id);
+        }
+        true
+    }
+
+    /// Add a command loading each example to the command palette
+    pub fn register_commands(&self, registry: &mut CommandRegistry) {
+        for example in &self.examples {
+            registry.register(
+                Command::new(
+                    CommandKind::LoadExample(example.id),
+                    tr_args(
+                        "command-palette-load-example",
+                        &[("example", &example.name)],
+                    ),
+                )
+                .with_keywords([example.category.to_string(), example.id.replace('_', " ")]),
+            );
+        }
+    }
+
+    /// Display name of the selected example
+    pub fn selected_example_name(&self) -> Option<&'static str> {
+        self.selected_example.map(|idx| self.examples[idx].name)
+    }
+
+    /// Current WGSL source of the shader editor
+    pub fn shader_source(&self) -> &str {
+        self.shader_editor.source_code()
+    }
+
     /// Export the shader editor state
     pub fn export_shader_editor_state(&self) -> crate::state::ShaderEditorState {
         self.shader_editor.export_state()
@@ -1716,10 +3800,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         let playground_state = crate::state::PlaygroundState {
             version: "1.0".to_string(),
             theme: crate::state::Theme::Dark,
+            language: None,
+            custom_theme: None,
             shader_editor: Some(crate::state::ShaderEditorState {
                 source_code: shader_source.to_string(),
                 label: "shader".to_string(),
                 file_path: "shader.wgsl".to_string(),
+                ..Default::default()
             }),
             buffer_panel: None,
             texture_panel: None,
@@ -1731,17 +3818,65 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             api_coverage: None,
             tutorial_state: None,
             learning_progress: None,
+            device_config: None,
         };
 
         self.export_to_standalone_project_with_state(&playground_state);
     }
 
-    /// Export the playground configuration to a standalone Rust project
+    /// Export the playground configuration to a standalone project for the
+    /// selected target
     pub fn export_to_standalone_project_with_state(
         &mut self,
         playground_state: &crate::state::PlaygroundState,
     ) {
-        use crate::code_generator::{CodeGenConfig, CodeGenerator};
+        use crate::code_generator::CodeGenConfig;
+
+        // Configure the code generator with full playground state
+        let config = CodeGenConfig::new(self.export_project_name.clone())
+            .with_canvas_size(self.canvas_width, self.canvas_height)
+            .with_clear_color(self.clear_color)
+            .with_playground_state(playground_state.clone())
+            .with_target(self.export_target);
+
+        self.generate_project(config);
+    }
+
+    /// Export a compute shader to a standalone project that runs the dispatch
+    /// on the playground's buffer and prints the result
+    ///
+    /// Without `workgroups`, enough workgroups are dispatched to cover the
+    /// buffer.
+    pub fn export_compute_project(
+        &mut self,
+        playground_state: &crate::state::PlaygroundState,
+        shader_source: &str,
+        workgroups: Option<[u32; 3]>,
+    ) {
+        use crate::code_generator::{CodeGenConfig, ExampleType, ExportTarget};
+
+        // Compute projects block on reading the buffer back, so only the
+        // Python target replaces the native Rust project
+        let target = match self.export_target {
+            ExportTarget::Python => ExportTarget::Python,
+            _ => ExportTarget::Native,
+        };
+        let mut config = CodeGenConfig::new(self.export_project_name.clone())
+            .with_example_type(ExampleType::Compute)
+            .with_shader(shader_source.to_string())
+            .with_playground_state(playground_state.clone())
+            .with_target(target);
+        if let Some([x, y, z]) = workgroups {
+            config = config.with_workgroups(x, y, z);
+        }
+
+        self.generate_project(config);
+    }
+
+    /// Generate a project into the user's home directory and report the
+    /// outcome in the export status
+    fn generate_project(&mut self, config: crate::code_generator::CodeGenConfig) {
+        use crate::code_generator::CodeGenerator;
 
         // Create output directory in user's home directory
         let output_path =
@@ -1751,12 +3886,6 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 std::path::PathBuf::from(&self.export_project_name)
             };
 
-        // Configure the code generator with full playground state
-        let config = CodeGenConfig::new(self.export_project_name.clone())
-            .with_canvas_size(self.canvas_width, self.canvas_height)
-            .with_clear_color(self.clear_color)
-            .with_playground_state(playground_state.clone());
-
         let generator = CodeGenerator::new(config);
 
         // Generate the project
@@ -1777,71 +3906,277 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     }
 }
 
-// Matrix math utilities
-fn identity_matrix() -> [[f32; 4]; 4] {
-    [
-        [1.0, 0.0, 0.0, 0.0],
-        [0.0, 1.0, 0.0, 0.0],
-        [0.0, 0.0, 1.0, 0.0],
-        [0.0, 0.0, 0.0, 1.0],
-    ]
+/// Size of the test pattern used when no texture is loaded
+const TEST_PATTERN_SIZE: u32 = 512;
+
+/// Upload an encoded image, or the test pattern without one, and return
+/// the error if the image could not be used
+///
+/// The test pattern is also uploaded when the image fails, so the example
+/// always has an input.
+fn upload_processing_image(
+    encoded: Option<&[u8]>,
+    device: &Device,
+    queue: &Queue,
+    processor: &mut ImageProcessor,
+) -> Option<String> {
+    let error = match encoded.map(image::load_from_memory) {
+        Some(Ok(image)) => {
+            let rgba = image.to_rgba8();
+            processor
+                .set_image(device, queue, rgba.as_raw(), rgba.width(), rgba.height())
+                .err()
+        }
+        Some(Err(e)) => Some(format!("Failed to decode the loaded texture: {}", e)),
+        None => None,
+    };
+    if encoded.is_none() || error.is_some() {
+        let pattern = test_pattern(TEST_PATTERN_SIZE, TEST_PATTERN_SIZE);
+        if let Err(e) = processor.set_image(
+            device,
+            queue,
+            &pattern,
+            TEST_PATTERN_SIZE,
+            TEST_PATTERN_SIZE,
+        ) {
+            log::error!("Failed to upload the test pattern: {}", e);
+        }
+    }
+    error
 }
 
-fn perspective_matrix(fov_y: f32, aspect: f32, near: f32, far: f32) -> [[f32; 4]; 4] {
-    let f = 1.0 / (fov_y / 2.0).tan();
-    [
-        [f / aspect, 0.0, 0.0, 0.0],
-        [0.0, f, 0.0, 0.0],
-        [0.0, 0.0, (far + near) / (near - far), -1.0],
-        [0.0, 0.0, (2.0 * far * near) / (near - far), 0.0],
-    ]
-}
+/// Timing and verification of the last algorithm run, in place of the canvas
+fn show_algorithm_results(ui: &mut egui::Ui, state: &AlgorithmState) {
+    let Some(comparison) = &state.comparison else {
+        if let Some(error) = &state.error {
+            ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+        }
+        return;
+    };
+    let millis = |time: std::time::Duration| format!("{:.3} ms", time.as_secs_f64() * 1000.0);
+    let result = match comparison.algorithm {
+        Algorithm::Reduction => "Result",
+        Algorithm::PrefixScan => "Last element",
+    };
+    ui.label(format!(
+        "{} of {} elements: {} = {}",
+        comparison.params.op.label(),
+        comparison.params.element_count,
+        result,
+        comparison.result
+    ));
+    egui::Grid::new("algorithm_results")
+        .num_columns(4)
+        .spacing([20.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new("Method").strong());
+            ui.label(egui::RichText::new("Time").strong());
+            ui.label(egui::RichText::new("Dispatches").strong());
+            ui.label(egui::RichText::new("Matches CPU").strong());
+            ui.end_row();
+
+            for result in &comparison.strategies {
+                ui.label(result.strategy.label(comparison.algorithm));
+                ui.label(millis(result.gpu_time));
+                ui.label(result.dispatches.to_string());
+                match result.mismatch {
+                    None => ui.colored_label(egui::Color32::from_rgb(100, 200, 100), "✓"),
+                    Some(index) => ui.colored_label(
+                        egui::Color32::from_rgb(220, 80, 80),
+                        format!("✗ at element {}", index),
+                    ),
+                };
+                ui.end_row();
+            }
 
-fn look_at_matrix(eye: [f32; 3], center: [f32; 3], up: [f32; 3]) -> [[f32; 4]; 4] {
-    let f = normalize([center[0] - eye[0], center[1] - eye[1], center[2] - eye[2]]);
-    let s = normalize(cross(f, up));
-    let u = cross(s, f);
-
-    [
-        [s[0], u[0], -f[0], 0.0],
-        [s[1], u[1], -f[1], 0.0],
-        [s[2], u[2], -f[2], 0.0],
-        [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
-    ]
+            ui.label("CPU (verification)");
+            ui.label(millis(comparison.cpu_time));
+            ui.label("-");
+            ui.label("-");
+            ui.end_row();
+        });
+    if let Some(speedup) = comparison.speedup() {
+        ui.label(format!(
+            "Workgroup shared memory ran {:.1}x as fast as the naive approach",
+            speedup
+        ));
+    }
 }
 
-fn rotation_x_matrix(angle: f32) -> [[f32; 4]; 4] {
-    let c = angle.cos();
-    let s = angle.sin();
-    [
-        [1.0, 0.0, 0.0, 0.0],
-        [0.0, c, s, 0.0],
-        [0.0, -s, c, 0.0],
-        [0.0, 0.0, 0.0, 1.0],
-    ]
+/// Timing and verification of the last sort, and the keys after every step
+/// of a small sort, in place of the canvas
+fn show_sort_results(ui: &mut egui::Ui, state: &mut SortState) {
+    if let Some(error) = &state.error {
+        ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+    }
+    let Some(comparison) = &state.comparison else {
+        return;
+    };
+    let millis = |time: std::time::Duration| format!("{:.3} ms", time.as_secs_f64() * 1000.0);
+    ui.label(format!(
+        "{} {} keys",
+        comparison.params.key_count,
+        comparison.params.key_type.label()
+    ));
+    egui::Grid::new("sort_results")
+        .num_columns(4)
+        .spacing([20.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new("Method").strong());
+            ui.label(egui::RichText::new("Time").strong());
+            ui.label(egui::RichText::new("Dispatches").strong());
+            ui.label(egui::RichText::new("Matches CPU").strong());
+            ui.end_row();
+
+            for result in &comparison.strategies {
+                ui.label(result.strategy.label());
+                ui.label(millis(result.gpu_time));
+                ui.label(result.passes.to_string());
+                match result.mismatch {
+                    None => ui.colored_label(egui::Color32::from_rgb(100, 200, 100), "✓"),
+                    Some(index) => ui.colored_label(
+                        egui::Color32::from_rgb(220, 80, 80),
+                        format!("✗ at key {}", index),
+                    ),
+                };
+                ui.end_row();
+            }
+
+            ui.label("CPU sort (verification)");
+            ui.label(millis(comparison.cpu_time));
+            ui.label("-");
+            ui.label("-");
+            ui.end_row();
+        });
+    if let Some(speedup) = comparison.speedup() {
+        ui.label(format!(
+            "Workgroup shared memory ran {:.1}x as fast as global memory only",
+            speedup
+        ));
+    }
+
+    let (Some(key_type), Some(width)) = (
+        state.snapshot_key_type,
+        state.snapshots.first().map(Vec::len),
+    ) else {
+        return;
+    };
+    ui.add_space(8.0);
+    ui.label(format!(
+        "{} keys after every step, top to bottom, blue the smallest and red the largest",
+        width
+    ));
+    let height = state.snapshots.len();
+    let image = state.image.get_or_insert_with(|| {
+        let pixels = snapshot_rgba(&state.snapshots, key_type);
+        ui.ctx().load_texture(
+            "bitonic_sort_steps",
+            egui::ColorImage::from_rgba_unmultiplied([width, height], &pixels),
+            egui::TextureOptions::NEAREST,
+        )
+    });
+    // Whole pixels per key and step
+    let scale = (ui.available_width() / width as f32).floor().max(1.0);
+    let size = egui::vec2(width as f32 * scale, height as f32 * scale * 4.0);
+    ui.add(egui::Image::new(egui::load::SizedTexture::new(
+        image.id(),
+        size,
+    )));
 }
 
-fn rotation_y_matrix(angle: f32) -> [[f32; 4]; 4] {
-    let c = angle.cos();
-    let s = angle.sin();
-    [
-        [c, 0.0, -s, 0.0],
-        [0.0, 1.0, 0.0, 0.0],
-        [s, 0.0, c, 0.0],
-        [0.0, 0.0, 0.0, 1.0],
-    ]
+/// Draw the unfiltered and filtered image side by side, split at a divider
+/// that follows the pointer while dragging
+#[cfg(not(target_arch = "wasm32"))]
+fn show_split_preview(
+    ui: &mut egui::Ui,
+    device: &Device,
+    renderer: &mut egui_wgpu::Renderer,
+    state: &mut ImageProcessingState,
+    split: &mut f32,
+    max_size: egui::Vec2,
+) {
+    let (Some((width, height)), Some((before, after))) = (
+        state.processor.size(),
+        state.processor.get_texture_ids(device, renderer),
+    ) else {
+        ui.colored_label(egui::Color32::RED, "Failed to register image textures");
+        return;
+    };
+
+    let scale = (max_size.x / width as f32).min(max_size.y / height as f32);
+    let size = egui::vec2(width as f32, height as f32) * scale;
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+    if let Some(pointer) = response.interact_pointer_pos() {
+        *split = ((pointer.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+    }
+
+    let divider = rect.left() + rect.width() * *split;
+    let painter = ui.painter_at(rect);
+    painter.image(
+        before,
+        egui::Rect::from_min_max(rect.min, egui::pos2(divider, rect.bottom())),
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(*split, 1.0)),
+        egui::Color32::WHITE,
+    );
+    painter.image(
+        after,
+        egui::Rect::from_min_max(egui::pos2(divider, rect.top()), rect.max),
+        egui::Rect::from_min_max(egui::pos2(*split, 0.0), egui::pos2(1.0, 1.0)),
+        egui::Color32::WHITE,
+    );
+    painter.line_segment(
+        [
+            egui::pos2(divider, rect.top()),
+            egui::pos2(divider, rect.bottom()),
+        ],
+        egui::Stroke::new(2.0, egui::Color32::WHITE),
+    );
+    let font = egui::FontId::proportional(14.0);
+    painter.text(
+        rect.left_top() + egui::vec2(6.0, 6.0),
+        egui::Align2::LEFT_TOP,
+        "Before",
+        font.clone(),
+        egui::Color32::WHITE,
+    );
+    painter.text(
+        rect.right_top() + egui::vec2(-6.0, 6.0),
+        egui::Align2::RIGHT_TOP,
+        "After",
+        font,
+        egui::Color32::WHITE,
+    );
+
+    ui.add_space(5.0);
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new("✓ Processing with compute shaders")
+                .color(egui::Color32::from_rgb(100, 255, 100)),
+        );
+        ui.label(
+            egui::RichText::new("💡 Drag across the image to move the divider")
+                .color(egui::Color32::GRAY)
+                .italics(),
+        );
+    });
 }
 
-fn matrix_multiply(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
-    let mut result = [[0.0; 4]; 4];
-    for i in 0..4 {
-        for j in 0..4 {
-            for k in 0..4 {
-                result[i][j] += a[i][k] * b[k][j];
+impl ResourceRecreate for RenderingPanel {
+    fn recreate_resources(&mut self, device: &Device, queue: &Queue) {
+        self.render_state = RenderState::None;
+        self.gpu_errors.clear_all();
+        self.pixel_picker.clear();
+        self.init_render_texture(device);
+        // Restart the running example so its pipelines exist on the new device
+        if self.is_example_running {
+            if let Some(idx) = self.selected_example {
+                let example_id = self.examples[idx].id;
+                self.start_example(device, queue, example_id);
             }
         }
     }
-    result
 }
 
 #[cfg(test)]
@@ -1851,8 +4186,9 @@ mod tests {
     #[test]
     fn test_rendering_panel_new_without_device() {
         let panel = RenderingPanel::new_without_device();
-        assert_eq!(panel.examples.len(), 4);
+        assert_eq!(panel.examples.len(), 14);
         assert_eq!(panel.selected_example, Some(0)); // First example is auto-selected
+        assert_eq!(panel.selected_example_name(), Some(panel.examples[0].name));
         assert!(!panel.show_source_code);
         assert_eq!(panel.category_filter, None);
         assert!(!panel.is_example_running);
@@ -1863,7 +4199,7 @@ mod tests {
     #[test]
     fn test_rendering_panel_default() {
         let panel = RenderingPanel::default();
-        assert_eq!(panel.examples.len(), 4);
+        assert_eq!(panel.examples.len(), 14);
         assert!(!panel.is_example_running);
     }
 
@@ -1893,6 +4229,7 @@ mod tests {
                 || example.id == "cube"
                 || example.id == "texture_mapping"
                 || example.id == "compute_shader"
+                || ImageFilter::from_example_id(example.id).is_some()
             {
                 assert!(
                     !tags.is_empty(),
@@ -1,4 +1,5 @@
 use crate::buffer::BufferUsages;
+use crate::memory_budget::MemoryBudget;
 use wgpu::{TextureDimension, TextureFormat, TextureUsages};
 
 /// Type alias for unique resource identifiers
@@ -448,6 +449,15 @@ impl ResourceInspectorPanel {
             .sum()
     }
 
+    /// Memory of all resources that are not destroyed, regardless of filters
+    pub fn tracked_memory(&self) -> u64 {
+        self.resources
+            .iter()
+            .filter(|r| r.state() != ResourceState::Destroyed)
+            .map(|r| r.memory_usage())
+            .sum()
+    }
+
     /// Format bytes into human-readable string
     fn format_bytes(bytes: u64) -> String {
         if bytes < 1024 {
@@ -463,6 +473,15 @@ impl ResourceInspectorPanel {
 
     /// Render the resource inspector UI
     pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.show(ui, None);
+    }
+
+    /// Render the resource inspector UI with usage against the GPU memory budget
+    pub fn ui_with_budget(&mut self, ui: &mut egui::Ui, budget: &mut MemoryBudget) {
+        self.show(ui, Some(budget));
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui, budget: Option<&mut MemoryBudget>) {
         ui.heading("🔍 Resource Inspector");
         ui.label("View and manage all created GPU resources");
         ui.add_space(10.0);
@@ -495,6 +514,11 @@ impl ResourceInspectorPanel {
 
         ui.add_space(10.0);
 
+        if let Some(budget) = budget {
+            budget.ui(ui);
+            ui.add_space(10.0);
+        }
+
         // Filter controls
         ui.group(|ui| {
             ui.heading("Filters");
@@ -884,6 +908,27 @@ mod tests {
         assert_eq!(resource.memory_usage(), 262144);
     }
 
+    #[test]
+    fn test_tracked_memory_ignores_filters_and_destroyed() {
+        let mut panel = ResourceInspectorPanel::new();
+        for (size, state) in [
+            (1024, ResourceState::Active),
+            (4096, ResourceState::Destroyed),
+        ] {
+            panel.add_buffer(BufferInfo {
+                id: 0,
+                label: None,
+                size,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+                state,
+            });
+        }
+        panel.filter = ResourceFilter::Textures;
+        assert_eq!(panel.total_memory_usage(), 0);
+        assert_eq!(panel.tracked_memory(), 1024);
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(ResourceInspectorPanel::format_bytes(512), "512 B");
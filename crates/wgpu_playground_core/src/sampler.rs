@@ -125,7 +125,7 @@ impl CompareFunction {
 /// Descriptor for creating a GPU sampler
 ///
 /// Samplers control how textures are sampled and filtered when accessed in shaders.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SamplerDescriptor {
     /// Optional label for debugging
     label: Option<String>,
@@ -1,3 +1,7 @@
+use crate::device_recovery::ResourceRecreate;
+use crate::i18n::{tr, tr_args};
+use crate::mip_debug::MipDebugPreview;
+use crate::operation_errors::OperationErrors;
 use crate::sampler::{
     AddressMode, CompareFunction, FilterMode, MipmapFilterMode, SamplerDescriptor,
 };
@@ -39,8 +43,23 @@ pub struct SamplerPanel {
     validation_error: Option<String>,
     /// Success message
     success_message: Option<String>,
+    /// Device errors from creating the sampler
+    gpu_errors: OperationErrors,
+    /// Mip level preview sampling with the current configuration
+    mip_preview: Option<MipDebugPreview>,
+    /// Configuration the preview's sampler was created from
+    preview_descriptor: Option<SamplerDescriptor>,
+    /// Why the current configuration can't be previewed
+    preview_error: Option<String>,
 }
 
+/// Operation name of sampler creation in [`OperationErrors`]
+const CREATE_SAMPLER: &str = "create_sampler";
+
+/// Operation name of the preview's sampler creation in [`OperationErrors`]
+#[cfg(not(target_arch = "wasm32"))]
+const PREVIEW_SAMPLER: &str = "preview_sampler";
+
 /// Border color options for UI selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum BorderColorChoice {
@@ -69,12 +88,12 @@ impl BorderColorChoice {
         ]
     }
 
-    fn name(&self) -> &'static str {
+    fn name(&self) -> String {
         match self {
-            BorderColorChoice::TransparentBlack => "Transparent Black",
-            BorderColorChoice::OpaqueBlack => "Opaque Black",
-            BorderColorChoice::OpaqueWhite => "Opaque White",
-            BorderColorChoice::Zero => "Zero",
+            BorderColorChoice::TransparentBlack => tr("sampler-border-transparent-black"),
+            BorderColorChoice::OpaqueBlack => tr("sampler-border-opaque-black"),
+            BorderColorChoice::OpaqueWhite => tr("sampler-border-opaque-white"),
+            BorderColorChoice::Zero => tr("sampler-border-zero"),
         }
     }
 }
@@ -106,6 +125,10 @@ impl SamplerPanel {
             border_color: BorderColorChoice::TransparentBlack,
             validation_error: None,
             success_message: None,
+            gpu_errors: OperationErrors::new(),
+            mip_preview: None,
+            preview_descriptor: None,
+            preview_error: None,
         }
     }
 
@@ -169,40 +192,143 @@ impl SamplerPanel {
             return None;
         }
 
-        match self.descriptor.create_sampler(device) {
+        let result = self.gpu_errors.scoped(device, CREATE_SAMPLER, || {
+            self.descriptor.create_sampler(device)
+        });
+        match result {
+            // The device rejected the descriptor; the error is shown inline
+            Ok(_) if self.gpu_errors.has_error(CREATE_SAMPLER) => {
+                self.success_message = None;
+                None
+            }
             Ok(sampler) => {
-                self.success_message = Some(format!(
-                    "✓ Sampler created successfully: {}",
-                    self.descriptor.label().unwrap_or("<unnamed>")
-                ));
+                let label = self
+                    .descriptor
+                    .label()
+                    .map_or_else(|| tr("sampler-unnamed"), str::to_string);
+                self.success_message = Some(tr_args("sampler-created", &[("label", &label)]));
                 self.validation_error = None;
                 Some(sampler)
             }
             Err(e) => {
-                self.validation_error = Some(format!("Failed to create sampler: {}", e));
+                self.validation_error = Some(tr_args("sampler-create-failed", &[("error", &e)]));
                 self.success_message = None;
                 None
             }
         }
     }
 
+    /// Recreate the preview's sampler if the configuration changed
+    #[cfg(not(target_arch = "wasm32"))]
+    fn update_preview_sampler(&mut self, device: &wgpu::Device) {
+        self.update_descriptor();
+        if self.preview_descriptor.as_ref() == Some(&self.descriptor) {
+            return;
+        }
+        self.preview_descriptor = Some(self.descriptor.clone());
+        self.preview_error = None;
+        self.gpu_errors.clear(PREVIEW_SAMPLER);
+
+        if self.descriptor.compare().is_some() {
+            self.preview_error = Some(tr("sampler-preview-compare"));
+            return;
+        }
+        let result = self.gpu_errors.scoped(device, PREVIEW_SAMPLER, || {
+            self.descriptor.create_sampler(device)
+        });
+        match result {
+            // The device rejected the descriptor; the error is shown inline
+            Ok(_) if self.gpu_errors.has_error(PREVIEW_SAMPLER) => {}
+            Ok(sampler) => {
+                let lod_clamp = (
+                    self.descriptor.lod_min_clamp(),
+                    self.descriptor.lod_max_clamp(),
+                );
+                if let Some(preview) = &mut self.mip_preview {
+                    preview.set_sampler(device, sampler, lod_clamp);
+                }
+            }
+            Err(e) => self.preview_error = Some(e.to_string()),
+        }
+    }
+
+    /// Preview of the mip levels the configured sampler reads
+    #[cfg(not(target_arch = "wasm32"))]
+    fn preview_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        ui.group(|ui| {
+            ui.heading(tr("sampler-preview-heading"));
+            ui.label(tr("sampler-preview-intro"));
+            ui.add_space(5.0);
+
+            if self.mip_preview.is_none() {
+                let mut preview = MipDebugPreview::new(device);
+                let size = 256;
+                let texture = crate::mip_debug::procedural_texture(size, size);
+                preview.set_image(device, queue, &texture, size, size, u32::MAX);
+                self.mip_preview = Some(preview);
+                self.preview_descriptor = None;
+            }
+            self.update_preview_sampler(device);
+
+            if let Some(error) = &self.preview_error {
+                ui.colored_label(
+                    egui::Color32::from_rgb(200, 200, 100),
+                    tr_args("sampler-preview-error", &[("error", error)]),
+                );
+            }
+            self.gpu_errors.ui(ui, PREVIEW_SAMPLER);
+            if let Some(preview) = &mut self.mip_preview {
+                preview.ui(ui, device, queue, renderer);
+            }
+        });
+        ui.add_space(10.0);
+    }
+
     /// Render the sampler configuration UI
     pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.ui_impl(ui, |_, _| {});
+    }
+
+    /// Render the sampler configuration UI with a preview of the mip levels
+    /// it samples
+    ///
+    /// Note: This method is only available when building for native targets.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ui_with_preview(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        self.ui_impl(ui, |panel, ui| {
+            panel.preview_ui(ui, device, queue, renderer)
+        });
+    }
+
+    /// The configuration UI, with `preview` drawn below the LOD settings
+    fn ui_impl(&mut self, ui: &mut egui::Ui, preview: impl FnOnce(&mut Self, &mut egui::Ui)) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("🎨 Sampler Configuration");
-            ui.label("Configure and create GPU samplers for texture sampling.");
+            ui.heading(tr("sampler-heading"));
+            ui.label(tr("sampler-intro"));
             ui.add_space(10.0);
 
             // Sampler Label
             ui.group(|ui| {
-                ui.heading("Sampler Properties");
+                ui.heading(tr("sampler-properties"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("sampler_properties")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Label:");
+                        ui.label(tr("sampler-label"));
                         ui.text_edit_singleline(&mut self.label_input);
                         ui.end_row();
                     });
@@ -212,23 +338,23 @@ impl SamplerPanel {
 
             // Address Modes
             ui.group(|ui| {
-                ui.heading("Address Modes");
-                ui.label("Control how texture coordinates outside [0, 1] are handled:");
+                ui.heading(tr("sampler-address-modes"));
+                ui.label(tr("sampler-address-modes-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("address_modes")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("U (horizontal):");
+                        ui.label(tr("sampler-address-u"));
                         Self::render_address_mode_combo(ui, &mut self.address_mode_u, "address_u");
                         ui.end_row();
 
-                        ui.label("V (vertical):");
+                        ui.label(tr("sampler-address-v"));
                         Self::render_address_mode_combo(ui, &mut self.address_mode_v, "address_v");
                         ui.end_row();
 
-                        ui.label("W (depth):");
+                        ui.label(tr("sampler-address-w"));
                         Self::render_address_mode_combo(ui, &mut self.address_mode_w, "address_w");
                         ui.end_row();
                     });
@@ -238,26 +364,26 @@ impl SamplerPanel {
 
             // Filter Modes
             ui.group(|ui| {
-                ui.heading("Filter Modes");
-                ui.label("Control how textures are sampled and filtered:");
+                ui.heading(tr("sampler-filter-modes"));
+                ui.label(tr("sampler-filter-modes-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("filter_modes")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Magnification (zoom in):")
-                            .on_hover_text("Filter when pixel is smaller than texel");
+                        ui.label(tr("sampler-mag-filter"))
+                            .on_hover_text(tr("sampler-mag-filter-hover"));
                         Self::render_filter_mode_combo(ui, &mut self.mag_filter, "mag_filter");
                         ui.end_row();
 
-                        ui.label("Minification (zoom out):")
-                            .on_hover_text("Filter when pixel is larger than texel");
+                        ui.label(tr("sampler-min-filter"))
+                            .on_hover_text(tr("sampler-min-filter-hover"));
                         Self::render_filter_mode_combo(ui, &mut self.min_filter, "min_filter");
                         ui.end_row();
 
-                        ui.label("Mipmap:")
-                            .on_hover_text("Filter between mipmap levels");
+                        ui.label(tr("sampler-mipmap-filter"))
+                            .on_hover_text(tr("sampler-mipmap-filter-hover"));
                         Self::render_mipmap_filter_combo(ui, &mut self.mipmap_filter, "mipmap_filter");
                         ui.end_row();
                     });
@@ -267,19 +393,19 @@ impl SamplerPanel {
 
             // LOD Clamping
             ui.group(|ui| {
-                ui.heading("LOD Clamping");
-                ui.label("Limit the level of detail range:");
+                ui.heading(tr("sampler-lod-heading"));
+                ui.label(tr("sampler-lod-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("lod_clamp")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        sampler::LOD_MIN_CLAMP.apply(ui.label("Min LOD:"));
+                        sampler::LOD_MIN_CLAMP.apply(ui.label(tr("sampler-lod-min")));
                         ui.text_edit_singleline(&mut self.lod_min_input);
                         ui.end_row();
 
-                        sampler::LOD_MAX_CLAMP.apply(ui.label("Max LOD:"));
+                        sampler::LOD_MAX_CLAMP.apply(ui.label(tr("sampler-lod-max")));
                         ui.text_edit_singleline(&mut self.lod_max_input);
                         ui.end_row();
                     });
@@ -287,16 +413,19 @@ impl SamplerPanel {
 
             ui.add_space(10.0);
 
+            preview(self, ui);
+
             // Anisotropic Filtering
             ui.group(|ui| {
-                ui.heading("Anisotropic Filtering");
-                sampler::MAX_ANISOTROPY.apply(
-                    ui.label("Improve texture quality at oblique angles (1 = disabled, 16 = maximum quality):")
-                );
+                ui.heading(tr("sampler-anisotropy-heading"));
+                sampler::MAX_ANISOTROPY.apply(ui.label(tr("sampler-anisotropy-intro")));
                 ui.add_space(5.0);
 
                 ui.horizontal(|ui| {
-                    ui.add(egui::Slider::new(&mut self.anisotropy, 1..=16).text("Level"));
+                    ui.add(
+                        egui::Slider::new(&mut self.anisotropy, 1..=16)
+                            .text(tr("sampler-anisotropy-level")),
+                    );
                 });
             });
 
@@ -304,16 +433,16 @@ impl SamplerPanel {
 
             // Comparison Function
             ui.group(|ui| {
-                ui.heading("Comparison Function");
-                ui.label("Optional depth/stencil comparison for shadow mapping:");
+                ui.heading(tr("sampler-compare-heading"));
+                ui.label(tr("sampler-compare-intro"));
                 ui.add_space(5.0);
 
-                ui.checkbox(&mut self.enable_compare, "Enable comparison");
+                ui.checkbox(&mut self.enable_compare, tr("sampler-compare-enable"));
 
                 if self.enable_compare {
                     ui.add_space(5.0);
                     ui.horizontal(|ui| {
-                        ui.label("Function:");
+                        ui.label(tr("sampler-compare-function"));
                         Self::render_compare_function_combo(ui, &mut self.compare_function, "compare_func");
                     });
                 }
@@ -323,16 +452,16 @@ impl SamplerPanel {
 
             // Border Color
             ui.group(|ui| {
-                ui.heading("Border Color");
-                sampler::BORDER_COLOR.apply(ui.label("Color used when address mode is ClampToBorder:"));
+                ui.heading(tr("sampler-border-heading"));
+                sampler::BORDER_COLOR.apply(ui.label(tr("sampler-border-intro")));
                 ui.add_space(5.0);
 
-                ui.checkbox(&mut self.enable_border_color, "Enable border color");
+                ui.checkbox(&mut self.enable_border_color, tr("sampler-border-enable"));
 
                 if self.enable_border_color {
                     ui.add_space(5.0);
                     ui.horizontal(|ui| {
-                        ui.label("Color:");
+                        ui.label(tr("sampler-border-color"));
                         Self::render_border_color_combo(ui, &mut self.border_color, "border_color");
                     });
                 }
@@ -346,7 +475,7 @@ impl SamplerPanel {
                     ui.add_space(5.0);
                     ui.colored_label(
                         egui::Color32::from_rgb(200, 200, 100),
-                        "⚠ Border color should be enabled when using ClampToBorder"
+                        tr("sampler-border-warning"),
                     );
                 }
             });
@@ -355,31 +484,35 @@ impl SamplerPanel {
 
             // Validation and Creation
             ui.horizontal(|ui| {
-                if ui.button("🔍 Validate").clicked() {
+                if ui.button(tr("sampler-validate")).clicked() {
                     self.validate();
                 }
 
-                if ui.button("✨ Create Sampler").clicked() {
+                if ui.button(tr("sampler-create")).clicked() {
                     // Note: In the actual implementation, we would need a device reference
                     // For now, we just validate
                     if self.validate() {
-                        self.success_message = Some(
-                            "✓ Configuration is valid. In a full implementation, the sampler would be created here."
-                                .to_string(),
-                        );
+                        self.success_message = Some(tr("sampler-valid"));
                     }
                 }
 
-                if ui.button("🔄 Reset").clicked() {
+                if ui.button(tr("sampler-reset")).clicked() {
+                    // Keep the preview's GPU resources and egui texture
+                    let mip_preview = self.mip_preview.take();
                     *self = Self::new();
+                    self.mip_preview = mip_preview;
                 }
             });
+            self.gpu_errors.ui(ui, CREATE_SAMPLER);
 
             ui.add_space(10.0);
 
             // Display validation errors or success messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("sampler-error", &[("error", error)]),
+                );
             }
 
             if let Some(success) = &self.success_message {
@@ -390,37 +523,68 @@ impl SamplerPanel {
 
             // Current Configuration Summary
             ui.group(|ui| {
-                ui.heading("Configuration Summary");
+                ui.heading(tr("sampler-summary-heading"));
                 ui.add_space(5.0);
 
                 self.update_descriptor();
 
+                let label = self
+                    .descriptor
+                    .label()
+                    .map_or_else(|| tr("sampler-summary-none"), str::to_string);
+                ui.monospace(tr_args("sampler-summary-label", &[("label", &label)]));
+                ui.monospace(format!(
+                    "{} {:?}",
+                    tr("sampler-summary-address-u"),
+                    self.descriptor.address_mode_u()
+                ));
+                ui.monospace(format!(
+                    "{} {:?}",
+                    tr("sampler-summary-address-v"),
+                    self.descriptor.address_mode_v()
+                ));
+                ui.monospace(format!(
+                    "{} {:?}",
+                    tr("sampler-summary-address-w"),
+                    self.descriptor.address_mode_w()
+                ));
+                ui.monospace(format!(
+                    "{} {:?}",
+                    tr("sampler-summary-mag-filter"),
+                    self.descriptor.mag_filter()
+                ));
+                ui.monospace(format!(
+                    "{} {:?}",
+                    tr("sampler-summary-min-filter"),
+                    self.descriptor.min_filter()
+                ));
                 ui.monospace(format!(
-                    "Label: {}",
-                    self.descriptor.label().unwrap_or("<none>")
+                    "{} {:?}",
+                    tr("sampler-summary-mipmap-filter"),
+                    self.descriptor.mipmap_filter()
                 ));
-                ui.monospace(format!("Address U: {:?}", self.descriptor.address_mode_u()));
-                ui.monospace(format!("Address V: {:?}", self.descriptor.address_mode_v()));
-                ui.monospace(format!("Address W: {:?}", self.descriptor.address_mode_w()));
-                ui.monospace(format!("Mag Filter: {:?}", self.descriptor.mag_filter()));
-                ui.monospace(format!("Min Filter: {:?}", self.descriptor.min_filter()));
-                ui.monospace(format!("Mipmap Filter: {:?}", self.descriptor.mipmap_filter()));
-                ui.monospace(format!("LOD Clamp: {:.1} - {:.1}", 
+                ui.monospace(format!(
+                    "{} {:.1} - {:.1}",
+                    tr("sampler-summary-lod-clamp"),
                     self.descriptor.lod_min_clamp(),
                     self.descriptor.lod_max_clamp()
                 ));
-                ui.monospace(format!("Anisotropy: {}", self.descriptor.anisotropy_clamp()));
+                ui.monospace(format!(
+                    "{} {}",
+                    tr("sampler-summary-anisotropy"),
+                    self.descriptor.anisotropy_clamp()
+                ));
 
                 if let Some(compare) = self.descriptor.compare() {
-                    ui.monospace(format!("Compare: {:?}", compare));
+                    ui.monospace(format!("{} {:?}", tr("sampler-summary-compare"), compare));
                 } else {
-                    ui.monospace("Compare: None");
+                    ui.monospace(tr("sampler-summary-compare-none"));
                 }
 
                 if let Some(border) = self.descriptor.border_color() {
-                    ui.monospace(format!("Border Color: {:?}", border));
+                    ui.monospace(format!("{} {:?}", tr("sampler-summary-border"), border));
                 } else {
-                    ui.monospace("Border Color: None");
+                    ui.monospace(tr("sampler-summary-border-none"));
                 }
             });
         });
@@ -587,33 +751,78 @@ impl SamplerPanel {
     }
 
     /// Import state from a serializable format
-    ///
-    /// Note: Address modes, filters, and compare function are stored as strings but are not
-    /// parsed back to avoid complexity. The panel will retain default values for these fields.
-    /// Future enhancement could add enum parsing support.
     pub fn import_state(&mut self, state: &crate::state::SamplerPanelState) {
+        use crate::state::parse_variant;
+
+        const ADDRESS_MODES: [AddressMode; 4] = [
+            AddressMode::ClampToEdge,
+            AddressMode::Repeat,
+            AddressMode::MirrorRepeat,
+            AddressMode::ClampToBorder,
+        ];
+        const FILTER_MODES: [FilterMode; 2] = [FilterMode::Nearest, FilterMode::Linear];
+        const MIPMAP_FILTER_MODES: [MipmapFilterMode; 2] =
+            [MipmapFilterMode::Nearest, MipmapFilterMode::Linear];
+        const COMPARE_FUNCTIONS: [CompareFunction; 8] = [
+            CompareFunction::Never,
+            CompareFunction::Less,
+            CompareFunction::Equal,
+            CompareFunction::LessEqual,
+            CompareFunction::Greater,
+            CompareFunction::NotEqual,
+            CompareFunction::GreaterEqual,
+            CompareFunction::Always,
+        ];
+
         self.label_input = state.label.clone();
         self.lod_min_input = state.lod_min_clamp.clone();
         self.lod_max_input = state.lod_max_clamp.clone();
         if let Ok(aniso) = state.max_anisotropy.parse::<u16>() {
             self.anisotropy = aniso.clamp(1, 16);
         }
+        for (field, name) in [
+            (&mut self.address_mode_u, &state.address_mode_u),
+            (&mut self.address_mode_v, &state.address_mode_v),
+            (&mut self.address_mode_w, &state.address_mode_w),
+        ] {
+            if let Some(mode) = parse_variant(name, &ADDRESS_MODES) {
+                *field = mode;
+            }
+        }
+        for (field, name) in [
+            (&mut self.mag_filter, &state.mag_filter),
+            (&mut self.min_filter, &state.min_filter),
+        ] {
+            if let Some(mode) = parse_variant(name, &FILTER_MODES) {
+                *field = mode;
+            }
+        }
+        if let Some(mode) = parse_variant(&state.mipmap_filter, &MIPMAP_FILTER_MODES) {
+            self.mipmap_filter = mode;
+        }
         self.enable_compare = state.compare.is_some();
-
-        // NOTE: Address modes, filters, and compare function are not parsed from strings.
-        // Current behavior: These fields reset to default values when loading state.
-        // To implement parsing:
-        // 1. Add parse_address_mode(&str) -> Option<AddressMode> helper
-        // 2. Add parse_filter_mode(&str) -> Option<FilterMode> helper
-        // 3. Add parse_compare_function(&str) -> Option<CompareFunction> helper
-        // 4. Use state.address_mode_u_str, mag_filter_str, etc. to restore values
-        // Alternative: Store enum discriminants as integers in state instead of strings
+        if let Some(compare) = state
+            .compare
+            .as_deref()
+            .and_then(|name| parse_variant(name, &COMPARE_FUNCTIONS))
+        {
+            self.compare_function = compare;
+        }
 
         self.validation_error = None;
         self.success_message = None;
     }
 }
 
+impl ResourceRecreate for SamplerPanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // The preview and its sampler are rebuilt on the next frame
+        self.mip_preview = None;
+        self.preview_descriptor = None;
+        self.gpu_errors.clear_all();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -879,4 +1088,22 @@ mod tests {
         assert!(all_colors.contains(&BorderColorChoice::OpaqueWhite));
         assert!(all_colors.contains(&BorderColorChoice::Zero));
     }
+
+    #[test]
+    fn test_sampler_state_roundtrip() {
+        let mut panel = SamplerPanel::new();
+        panel.address_mode_v = AddressMode::MirrorRepeat;
+        panel.min_filter = FilterMode::Linear;
+        panel.mipmap_filter = MipmapFilterMode::Linear;
+        panel.enable_compare = true;
+        panel.compare_function = CompareFunction::GreaterEqual;
+
+        let mut restored = SamplerPanel::new();
+        restored.import_state(&panel.export_state());
+        assert_eq!(restored.address_mode_v, AddressMode::MirrorRepeat);
+        assert_eq!(restored.min_filter, FilterMode::Linear);
+        assert_eq!(restored.mipmap_filter, MipmapFilterMode::Linear);
+        assert!(restored.enable_compare);
+        assert_eq!(restored.compare_function, CompareFunction::GreaterEqual);
+    }
 }
@@ -0,0 +1,796 @@
+//! Minimal scene graph for the pipeline previews
+//!
+//! A [`Scene`] is a forest of named nodes. Each node has a translation,
+//! rotation and scale relative to its parent, and may reference one of the
+//! preview meshes and one of the scene's materials. The preview renderers
+//! draw [`Scene::draw_list`], so several objects can be composed to see how
+//! a pipeline configuration handles overlap, depth and blending.
+//!
+//! [`SceneEditor`] is the tree view used to add, remove and reparent nodes
+//! and to edit their transforms.
+
+use crate::geometry::Primitive;
+use crate::math_utils::{mat4_mul, rotation_x, rotation_y, rotation_z, scaling, translation, Mat4};
+
+/// Identifies a node; ids are never reused within a scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u32);
+
+/// Geometry the preview renderers can draw
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeshRef {
+    /// Unit cube with a different color on each face
+    Cube,
+    /// Unit square in the XZ plane, facing up
+    Plane,
+    /// Generated sphere, torus, cylinder, grid or icosphere
+    Primitive(Primitive),
+}
+
+impl MeshRef {
+    /// Every mesh, primitives with their default parameters
+    pub const ALL: [MeshRef; 7] = [
+        MeshRef::Cube,
+        MeshRef::Plane,
+        MeshRef::Primitive(Primitive::ALL[0]),
+        MeshRef::Primitive(Primitive::ALL[1]),
+        MeshRef::Primitive(Primitive::ALL[2]),
+        MeshRef::Primitive(Primitive::ALL[3]),
+        MeshRef::Primitive(Primitive::ALL[4]),
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MeshRef::Cube => "Cube",
+            MeshRef::Plane => "Plane",
+            MeshRef::Primitive(primitive) => primitive.name(),
+        }
+    }
+
+    /// Whether both are the same mesh, ignoring primitive parameters
+    pub fn same_kind(self, other: MeshRef) -> bool {
+        match (self, other) {
+            (MeshRef::Primitive(a), MeshRef::Primitive(b)) => a.same_kind(b),
+            _ => self == other,
+        }
+    }
+}
+
+/// Index of a material in [`Scene::materials`]
+pub type MaterialRef = usize;
+
+/// Surface appearance shared by any number of nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material {
+    pub name: String,
+    /// Multiplied with the vertex colors; alpha feeds the blend state
+    pub tint: [f32; 4],
+}
+
+impl Material {
+    pub fn new(name: impl Into<String>, tint: [f32; 4]) -> Self {
+        Self {
+            name: name.into(),
+            tint,
+        }
+    }
+}
+
+/// Translation, rotation and scale relative to the parent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translation: [f32; 3],
+    /// Euler angles in degrees, applied around X, then Y, then Z
+    pub rotation: [f32; 3],
+    pub scale: [f32; 3],
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        translation: [0.0; 3],
+        rotation: [0.0; 3],
+        scale: [1.0; 3],
+    };
+
+    /// An unrotated, unscaled transform at `position`
+    pub fn at(position: [f32; 3]) -> Self {
+        Self {
+            translation: position,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Local-to-parent matrix
+    pub fn matrix(&self) -> Mat4 {
+        let [x, y, z] = self.rotation.map(f32::to_radians);
+        let rotation = mat4_mul(&rotation_z(z), &mat4_mul(&rotation_y(y), &rotation_x(x)));
+        mat4_mul(
+            &translation(self.translation),
+            &mat4_mul(&rotation, &scaling(self.scale)),
+        )
+    }
+}
+
+/// One entry in the scene tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub name: String,
+    pub transform: Transform,
+    pub mesh: Option<MeshRef>,
+    pub material: MaterialRef,
+    parent: Option<NodeId>,
+}
+
+impl Node {
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+}
+
+/// A mesh to draw and where
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawItem {
+    pub node: NodeId,
+    pub mesh: MeshRef,
+    /// Local-to-world matrix
+    pub model: Mat4,
+    pub tint: [f32; 4],
+}
+
+/// Error editing the scene tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneError {
+    /// No node has this id
+    UnknownNode(NodeId),
+    /// The new parent is the node itself or one of its descendants
+    Cycle { node: NodeId, parent: NodeId },
+}
+
+impl std::fmt::Display for SceneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SceneError::UnknownNode(id) => write!(f, "No scene node with id {}", id.0),
+            SceneError::Cycle { node, parent } => write!(
+                f,
+                "Node {} can't be parented to {}, which is inside its own subtree",
+                node.0, parent.0
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+/// Nodes with parent/child transforms, plus the materials they use
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scene {
+    /// Kept in insertion order, which is also the order among siblings
+    nodes: Vec<(NodeId, Node)>,
+    next_id: u32,
+    /// Never empty; material 0 is the default for new nodes
+    materials: Vec<Material>,
+    /// Whether the previews spin the whole scene over time
+    pub spin: bool,
+}
+
+impl Default for Scene {
+    /// A single spinning cube, the preview's classic look
+    fn default() -> Self {
+        let mut scene = Self::empty();
+        scene
+            .add(None, "Cube", Some(MeshRef::Cube))
+            .expect("root nodes have no parent to miss");
+        scene.spin = true;
+        scene
+    }
+}
+
+impl Scene {
+    /// A scene without nodes, holding only the default material
+    pub fn empty() -> Self {
+        Self {
+            nodes: Vec::new(),
+            next_id: 0,
+            materials: vec![Material::new("Vertex colors", [1.0; 4])],
+            spin: false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&Node> {
+        self.nodes
+            .iter()
+            .find(|(node_id, _)| *node_id == id)
+            .map(|(_, node)| node)
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes
+            .iter_mut()
+            .find(|(node_id, _)| *node_id == id)
+            .map(|(_, node)| node)
+    }
+
+    /// Nodes without a parent
+    pub fn roots(&self) -> Vec<NodeId> {
+        self.children_of(None)
+    }
+
+    /// Direct children of `id`
+    pub fn children(&self, id: NodeId) -> Vec<NodeId> {
+        self.children_of(Some(id))
+    }
+
+    fn children_of(&self, parent: Option<NodeId>) -> Vec<NodeId> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.parent == parent)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Every node in depth-first order, with its depth in the tree
+    pub fn depth_first(&self) -> Vec<(NodeId, usize)> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack: Vec<(NodeId, usize)> =
+            self.roots().into_iter().rev().map(|id| (id, 0)).collect();
+        while let Some((id, depth)) = stack.pop() {
+            order.push((id, depth));
+            stack.extend(
+                self.children(id)
+                    .into_iter()
+                    .rev()
+                    .map(|child| (child, depth + 1)),
+            );
+        }
+        order
+    }
+
+    /// Whether `node` is `ancestor` or lies in its subtree
+    pub fn is_in_subtree(&self, node: NodeId, ancestor: NodeId) -> bool {
+        let mut current = Some(node);
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            current = self.get(id).and_then(|node| node.parent);
+        }
+        false
+    }
+
+    /// Add a node with an identity transform and the default material
+    pub fn add(
+        &mut self,
+        parent: Option<NodeId>,
+        name: impl Into<String>,
+        mesh: Option<MeshRef>,
+    ) -> Result<NodeId, SceneError> {
+        if let Some(parent) = parent {
+            if !self.contains(parent) {
+                return Err(SceneError::UnknownNode(parent));
+            }
+        }
+        let id = NodeId(self.next_id);
+        self.next_id += 1;
+        self.nodes.push((
+            id,
+            Node {
+                name: name.into(),
+                transform: Transform::IDENTITY,
+                mesh,
+                material: 0,
+                parent,
+            },
+        ));
+        Ok(id)
+    }
+
+    /// Remove a node and its whole subtree, returning how many nodes went
+    pub fn remove(&mut self, id: NodeId) -> Result<usize, SceneError> {
+        if !self.contains(id) {
+            return Err(SceneError::UnknownNode(id));
+        }
+        let doomed: Vec<NodeId> = self
+            .nodes
+            .iter()
+            .map(|(node_id, _)| *node_id)
+            .filter(|&node_id| self.is_in_subtree(node_id, id))
+            .collect();
+        self.nodes.retain(|(node_id, _)| !doomed.contains(node_id));
+        Ok(doomed.len())
+    }
+
+    /// Move a node, with its subtree, under `parent` or to the top level
+    ///
+    /// The local transform is kept, so the node moves along with its new
+    /// parent rather than staying put in world space.
+    pub fn reparent(&mut self, id: NodeId, parent: Option<NodeId>) -> Result<(), SceneError> {
+        if !self.contains(id) {
+            return Err(SceneError::UnknownNode(id));
+        }
+        if let Some(parent) = parent {
+            if !self.contains(parent) {
+                return Err(SceneError::UnknownNode(parent));
+            }
+            if self.is_in_subtree(parent, id) {
+                return Err(SceneError::Cycle { node: id, parent });
+            }
+        }
+        // Move to the end so the node becomes the last of its new siblings
+        let index = self
+            .nodes
+            .iter()
+            .position(|(node_id, _)| *node_id == id)
+            .expect("checked above");
+        let mut entry = self.nodes.remove(index);
+        entry.1.parent = parent;
+        self.nodes.push(entry);
+        Ok(())
+    }
+
+    /// Local-to-world matrix of a node
+    pub fn world_matrix(&self, id: NodeId) -> Option<Mat4> {
+        let node = self.get(id)?;
+        let local = node.transform.matrix();
+        Some(match node.parent {
+            Some(parent) => mat4_mul(&self.world_matrix(parent)?, &local),
+            None => local,
+        })
+    }
+
+    /// Every node with a mesh, in depth-first order
+    pub fn draw_list(&self) -> Vec<DrawItem> {
+        self.depth_first()
+            .into_iter()
+            .filter_map(|(id, _)| {
+                let node = self.get(id)?;
+                Some(DrawItem {
+                    node: id,
+                    mesh: node.mesh?,
+                    model: self.world_matrix(id)?,
+                    tint: self.material(node.material).tint,
+                })
+            })
+            .collect()
+    }
+
+    pub fn materials(&self) -> &[Material] {
+        &self.materials
+    }
+
+    /// The material at `index`, or the default one if it doesn't exist
+    pub fn material(&self, index: MaterialRef) -> &Material {
+        self.materials.get(index).unwrap_or(&self.materials[0])
+    }
+
+    pub fn material_mut(&mut self, index: MaterialRef) -> Option<&mut Material> {
+        self.materials.get_mut(index)
+    }
+
+    pub fn add_material(&mut self, material: Material) -> MaterialRef {
+        self.materials.push(material);
+        self.materials.len() - 1
+    }
+
+    /// A name like `base` that no node uses yet
+    fn unique_name(&self, base: &str) -> String {
+        let taken = |name: &str| self.nodes.iter().any(|(_, node)| node.name == name);
+        if !taken(base) {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{} {}", base, n))
+            .find(|name| !taken(name))
+            .expect("unbounded range")
+    }
+}
+
+/// Axis colors for the transform widgets, as in most 3D tools
+const AXIS_COLORS: [egui::Color32; 3] = [
+    egui::Color32::from_rgb(230, 80, 80),
+    egui::Color32::from_rgb(110, 200, 90),
+    egui::Color32::from_rgb(90, 140, 240),
+];
+
+/// Tree view and transform editor for a [`Scene`]
+#[derive(Debug, Clone, Default)]
+pub struct SceneEditor {
+    selected: Option<NodeId>,
+    error: Option<String>,
+}
+
+impl SceneEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn selected(&self) -> Option<NodeId> {
+        self.selected
+    }
+
+    pub fn select(&mut self, id: Option<NodeId>) {
+        self.selected = id;
+    }
+
+    /// Node tree, structure buttons and the selected node's properties
+    pub fn ui(&mut self, ui: &mut egui::Ui, scene: &mut Scene) {
+        if self.selected.is_some_and(|id| !scene.contains(id)) {
+            self.selected = None;
+        }
+
+        ui.checkbox(&mut scene.spin, "Spin the scene");
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("➕ Node")
+                .on_hover_text("Add a cube at the top level")
+                .clicked()
+            {
+                self.add(scene, None);
+            }
+            ui.add_enabled_ui(self.selected.is_some(), |ui| {
+                if ui
+                    .button("➕ Child")
+                    .on_hover_text("Add a cube under the selected node")
+                    .clicked()
+                {
+                    self.add(scene, self.selected);
+                }
+                if ui
+                    .button("🗑 Remove")
+                    .on_hover_text("Remove the selected node and its children")
+                    .clicked()
+                {
+                    if let Some(id) = self.selected.take() {
+                        let _ = scene.remove(id);
+                    }
+                }
+            });
+        });
+
+        egui::ScrollArea::vertical()
+            .id_salt("scene_tree")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                if scene.is_empty() {
+                    ui.weak("Empty scene");
+                }
+                for (id, depth) in scene.depth_first() {
+                    let Some(node) = scene.get(id) else {
+                        continue;
+                    };
+                    let icon = if node.mesh.is_some() { "🧊" } else { "⊹" };
+                    ui.horizontal(|ui| {
+                        ui.add_space(depth as f32 * 16.0);
+                        let label = format!("{} {}", icon, node.name);
+                        if ui
+                            .selectable_label(self.selected == Some(id), label)
+                            .clicked()
+                        {
+                            self.selected = Some(id);
+                            self.error = None;
+                        }
+                    });
+                }
+            });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        if let Some(id) = self.selected {
+            ui.separator();
+            self.node_ui(ui, scene, id);
+        }
+    }
+
+    fn add(&mut self, scene: &mut Scene, parent: Option<NodeId>) {
+        let name = scene.unique_name("Cube");
+        if let Ok(id) = scene.add(parent, name, Some(MeshRef::Cube)) {
+            // Offset siblings so the new node doesn't hide inside another
+            let siblings = match parent {
+                Some(parent) => scene.children(parent).len(),
+                None => scene.roots().len(),
+            };
+            if let Some(node) = scene.get_mut(id) {
+                node.transform.translation[0] = (siblings - 1) as f32 * 1.5;
+            }
+            self.selected = Some(id);
+        }
+    }
+
+    /// Name, parent, mesh, material and transform of one node
+    fn node_ui(&mut self, ui: &mut egui::Ui, scene: &mut Scene, id: NodeId) {
+        // Every node outside the selected subtree is a valid parent
+        let parents: Vec<(NodeId, String)> = scene
+            .depth_first()
+            .into_iter()
+            .filter(|&(other, _)| !scene.is_in_subtree(other, id))
+            .filter_map(|(other, _)| Some((other, scene.get(other)?.name.clone())))
+            .collect();
+        let materials: Vec<String> = scene.materials().iter().map(|m| m.name.clone()).collect();
+        let Some(node) = scene.get(id) else {
+            return;
+        };
+        let current_parent = node.parent;
+        let parent_name = current_parent
+            .and_then(|parent| scene.get(parent))
+            .map_or("(none)".to_string(), |parent| parent.name.clone());
+
+        let mut new_parent = current_parent;
+        let Some(node) = scene.get_mut(id) else {
+            return;
+        };
+        egui::Grid::new("scene_node")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut node.name);
+                ui.end_row();
+
+                ui.label("Parent:");
+                egui::ComboBox::from_id_salt("scene_node_parent")
+                    .selected_text(parent_name)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut new_parent, None, "(none)");
+                        for (parent, name) in &parents {
+                            ui.selectable_value(&mut new_parent, Some(*parent), name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Mesh:");
+                egui::ComboBox::from_id_salt("scene_node_mesh")
+                    .selected_text(node.mesh.map_or("(none)", MeshRef::name))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut node.mesh, None, "(none)");
+                        for mesh in MeshRef::ALL {
+                            // Keep the parameters when the kind is unchanged
+                            let selected = node.mesh.is_some_and(|m| m.same_kind(mesh));
+                            if ui.selectable_label(selected, mesh.name()).clicked() && !selected {
+                                node.mesh = Some(mesh);
+                            }
+                        }
+                    });
+                ui.end_row();
+
+                if let Some(MeshRef::Primitive(primitive)) = &mut node.mesh {
+                    ui.label("Shape:");
+                    ui.vertical(|ui| primitive.ui(ui));
+                    ui.end_row();
+                }
+
+                ui.label("Material:");
+                egui::ComboBox::from_id_salt("scene_node_material")
+                    .selected_text(materials.get(node.material).map_or("?", String::as_str))
+                    .show_ui(ui, |ui| {
+                        for (index, name) in materials.iter().enumerate() {
+                            ui.selectable_value(&mut node.material, index, name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Translation:");
+                axis_drags(ui, &mut node.transform.translation, 0.01, 0.0, "");
+                ui.end_row();
+
+                ui.label("Rotation:");
+                axis_drags(ui, &mut node.transform.rotation, 0.5, 0.0, "°");
+                ui.end_row();
+
+                ui.label("Scale:");
+                axis_drags(ui, &mut node.transform.scale, 0.01, 1.0, "");
+                ui.end_row();
+            });
+
+        if new_parent != current_parent {
+            self.error = scene.reparent(id, new_parent).err().map(|e| e.to_string());
+        }
+
+        let material = scene.get(id).map_or(0, |node| node.material);
+        ui.horizontal(|ui| {
+            if ui
+                .button("New material")
+                .on_hover_text("Give this node its own copy of its material")
+                .clicked()
+            {
+                let mut copy = scene.material(material).clone();
+                copy.name = format!("Material {}", scene.materials().len());
+                let index = scene.add_material(copy);
+                if let Some(node) = scene.get_mut(id) {
+                    node.material = index;
+                }
+            }
+            if let Some(material) = scene.material_mut(material) {
+                ui.label("Tint:");
+                ui.color_edit_button_rgba_unmultiplied(&mut material.tint);
+            }
+        });
+    }
+}
+
+/// Gizmo-style X/Y/Z drags; double-clicking an axis label resets it
+fn axis_drags(ui: &mut egui::Ui, values: &mut [f32; 3], speed: f64, reset: f32, suffix: &str) {
+    ui.horizontal(|ui| {
+        for (axis, value) in values.iter_mut().enumerate() {
+            let label = ui
+                .add(
+                    egui::Label::new(
+                        egui::RichText::new(["X", "Y", "Z"][axis])
+                            .strong()
+                            .color(AXIS_COLORS[axis]),
+                    )
+                    .sense(egui::Sense::click()),
+                )
+                .on_hover_text("Drag the value; double-click here to reset");
+            if label.double_clicked() {
+                *value = reset;
+            }
+            ui.add(
+                egui::DragValue::new(value)
+                    .speed(speed)
+                    .max_decimals(2)
+                    .suffix(suffix),
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_utils::transform_point;
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_default_scene_is_one_spinning_cube() {
+        let scene = Scene::default();
+        assert!(scene.spin);
+        let draws = scene.draw_list();
+        assert_eq!(draws.len(), 1);
+        assert_eq!(draws[0].mesh, MeshRef::Cube);
+        assert_eq!(draws[0].tint, [1.0; 4]);
+        assert_close(transform_point(&draws[0].model, [0.5; 3]), [0.5; 3]);
+    }
+
+    #[test]
+    fn test_child_transform_is_relative_to_parent() {
+        let mut scene = Scene::empty();
+        let parent = scene.add(None, "Parent", None).unwrap();
+        let child = scene
+            .add(Some(parent), "Child", Some(MeshRef::Cube))
+            .unwrap();
+        scene.get_mut(parent).unwrap().transform = Transform {
+            translation: [1.0, 0.0, 0.0],
+            rotation: [0.0, 90.0, 0.0],
+            scale: [2.0; 3],
+        };
+        scene.get_mut(child).unwrap().transform = Transform::at([0.0, 0.0, 1.0]);
+
+        // The child's +Z offset is doubled, turned to +X and moved by 1
+        let world = scene.world_matrix(child).unwrap();
+        assert_close(transform_point(&world, [0.0; 3]), [3.0, 0.0, 0.0]);
+
+        let draws = scene.draw_list();
+        assert_eq!(draws.len(), 1, "nodes without a mesh aren't drawn");
+        assert_eq!(draws[0].node, child);
+    }
+
+    #[test]
+    fn test_rotation_order_is_x_then_y_then_z() {
+        let transform = Transform {
+            rotation: [90.0, 90.0, 0.0],
+            ..Transform::IDENTITY
+        };
+        // +Y turns to +Z around X, then to +X around Y
+        assert_close(
+            transform_point(&transform.matrix(), [0.0, 1.0, 0.0]),
+            [1.0, 0.0, 0.0],
+        );
+    }
+
+    #[test]
+    fn test_remove_takes_the_subtree() {
+        let mut scene = Scene::empty();
+        let a = scene.add(None, "A", None).unwrap();
+        let b = scene.add(Some(a), "B", None).unwrap();
+        let c = scene.add(Some(b), "C", None).unwrap();
+        let d = scene.add(None, "D", None).unwrap();
+
+        assert_eq!(scene.remove(b), Ok(2));
+        assert!(!scene.contains(c));
+        assert_eq!(scene.depth_first(), vec![(a, 0), (d, 0)]);
+        assert_eq!(scene.remove(b), Err(SceneError::UnknownNode(b)));
+    }
+
+    #[test]
+    fn test_reparent_rejects_cycles() {
+        let mut scene = Scene::empty();
+        let a = scene.add(None, "A", None).unwrap();
+        let b = scene.add(Some(a), "B", None).unwrap();
+        let c = scene.add(None, "C", None).unwrap();
+
+        assert_eq!(
+            scene.reparent(a, Some(b)),
+            Err(SceneError::Cycle { node: a, parent: b })
+        );
+        assert_eq!(
+            scene.reparent(a, Some(a)),
+            Err(SceneError::Cycle { node: a, parent: a })
+        );
+
+        scene.reparent(a, Some(c)).unwrap();
+        assert_eq!(scene.depth_first(), vec![(c, 0), (a, 1), (b, 2)]);
+        scene.reparent(b, None).unwrap();
+        assert_eq!(scene.roots(), vec![c, b]);
+    }
+
+    #[test]
+    fn test_ids_are_not_reused_and_names_stay_unique() {
+        let mut scene = Scene::empty();
+        let a = scene.add(None, scene.unique_name("Cube"), None).unwrap();
+        scene.add(None, scene.unique_name("Cube"), None).unwrap();
+        assert_eq!(scene.unique_name("Cube"), "Cube 3");
+        scene.remove(a).unwrap();
+        let b = scene.add(None, "B", None).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(scene.unique_name("Cube"), "Cube");
+    }
+
+    #[test]
+    fn test_nodes_share_materials() {
+        let mut scene = Scene::empty();
+        let red = scene.add_material(Material::new("Red", [1.0, 0.0, 0.0, 0.5]));
+        let a = scene.add(None, "A", Some(MeshRef::Cube)).unwrap();
+        let b = scene.add(None, "B", Some(MeshRef::Plane)).unwrap();
+        scene.get_mut(a).unwrap().material = red;
+        scene.get_mut(b).unwrap().material = red;
+        scene.material_mut(red).unwrap().tint[3] = 0.25;
+
+        let tints: Vec<f32> = scene.draw_list().iter().map(|d| d.tint[3]).collect();
+        assert_eq!(tints, vec![0.25, 0.25]);
+        // Dangling references fall back to the default material
+        scene.get_mut(b).unwrap().material = 99;
+        assert_eq!(scene.draw_list()[1].tint, [1.0; 4]);
+    }
+
+    #[test]
+    fn test_primitive_meshes_keep_their_parameters() {
+        let fine = MeshRef::Primitive(Primitive::Sphere {
+            segments: 64,
+            rings: 32,
+        });
+        assert!(fine.same_kind(MeshRef::ALL[2]));
+        assert_ne!(fine, MeshRef::ALL[2]);
+        assert!(!fine.same_kind(MeshRef::Cube));
+        assert!(!MeshRef::ALL[3].same_kind(MeshRef::ALL[4]));
+        assert_eq!(fine.name(), "Sphere");
+
+        let mut scene = Scene::empty();
+        scene.add(None, "Ball", Some(fine)).unwrap();
+        assert_eq!(scene.draw_list()[0].mesh, fine);
+    }
+}
@@ -0,0 +1,536 @@
+//! Gallery of ray-marched signed distance field shaders
+//!
+//! Every entry is a [fragment playground](crate::fragment_playground)
+//! shader: a `map` function giving the distance from a point to the
+//! nearest surface of the scene, and a `mainImage` that sphere traces a ray
+//! per pixel through it. They share [`SDF_LIBRARY`], appended after each
+//! shader, with the distance functions of a few primitives, smooth
+//! blending, the marcher, normals, soft shadows and ambient occlusion.
+//!
+//! The parameters of an entry are fields of a uniform buffer at
+//! `@group(1)`, so the playground reflects them like any other uniform;
+//! [`SdfParam`] adds the range and starting value of each for its panel.
+
+/// Functions every gallery shader can call; they call back into `map`
+pub const SDF_LIBRARY: &str = r#"
+// ---- SDF library, shared by the gallery ----
+// The shader above defines map(p), returning the distance to the scene
+// and a material number
+
+struct Ray {
+    origin: vec3<f32>,
+    direction: vec3<f32>,
+}
+
+struct Hit {
+    distance: f32,
+    // Material from map, or -1 where the ray escapes
+    material: f32,
+    steps: i32,
+}
+
+fn sd_sphere(p: vec3<f32>, radius: f32) -> f32 {
+    return length(p) - radius;
+}
+
+fn sd_box(p: vec3<f32>, half_size: vec3<f32>) -> f32 {
+    let q = abs(p) - half_size;
+    return length(max(q, vec3<f32>(0.0))) + min(max(q.x, max(q.y, q.z)), 0.0);
+}
+
+fn sd_round_box(p: vec3<f32>, half_size: vec3<f32>, radius: f32) -> f32 {
+    return sd_box(p, half_size - radius) - radius;
+}
+
+// `radii.x` around the y axis, `radii.y` of the tube
+fn sd_torus(p: vec3<f32>, radii: vec2<f32>) -> f32 {
+    let q = vec2<f32>(length(p.xz) - radii.x, p.y);
+    return length(q) - radii.y;
+}
+
+fn sd_capsule(p: vec3<f32>, a: vec3<f32>, b: vec3<f32>, radius: f32) -> f32 {
+    let pa = p - a;
+    let ba = b - a;
+    let h = clamp(dot(pa, ba) / dot(ba, ba), 0.0, 1.0);
+    return length(pa - ba * h) - radius;
+}
+
+// Union that rounds off the seam over a distance `k`
+fn op_smooth_union(a: f32, b: f32, k: f32) -> f32 {
+    let h = clamp(0.5 + 0.5 * (b - a) / max(k, 1e-4), 0.0, 1.0);
+    return mix(b, a, h) - k * h * (1.0 - h);
+}
+
+fn rotate_y(p: vec3<f32>, angle: f32) -> vec3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec3<f32>(c * p.x + s * p.z, p.y, c * p.z - s * p.x);
+}
+
+// Yaw and pitch of the camera; dragging on the preview turns it
+fn mouse_orbit() -> vec2<f32> {
+    if all(iMouse.xy == vec2<f32>(0.0)) {
+        return vec2<f32>(0.6, 0.35);
+    }
+    let mouse = iMouse.xy / iResolution.xy;
+    return vec2<f32>((mouse.x - 0.5) * 6.2831853, mix(0.05, 1.4, mouse.y));
+}
+
+// Ray through a pixel of a camera orbiting `center`
+fn orbit_camera(frag_coord: vec2<f32>, center: vec3<f32>, distance: f32, yaw: f32, pitch: f32) -> Ray {
+    let uv = (2.0 * frag_coord - iResolution.xy) / iResolution.y;
+    let origin = center + distance * vec3<f32>(cos(pitch) * sin(yaw), sin(pitch), cos(pitch) * cos(yaw));
+    let forward = normalize(center - origin);
+    let right = normalize(cross(forward, vec3<f32>(0.0, 1.0, 0.0)));
+    let up = cross(right, forward);
+    return Ray(origin, normalize(uv.x * right + uv.y * up + 1.8 * forward));
+}
+
+// Sphere tracing: step along the ray by the distance to the nearest
+// surface, which can't overshoot it
+fn march(ray: Ray, max_steps: i32, max_distance: f32) -> Hit {
+    var t = 0.0;
+    for (var i = 0; i < max_steps; i++) {
+        let d = map(ray.origin + ray.direction * t);
+        // The tolerance grows with distance, like the size of a pixel
+        if d.x < 0.0005 * t {
+            return Hit(t, d.y, i + 1);
+        }
+        t += d.x;
+        if t > max_distance {
+            return Hit(t, -1.0, i + 1);
+        }
+    }
+    return Hit(t, -1.0, max_steps);
+}
+
+// Gradient of the distance from four samples on a tetrahedron
+fn normal_at(p: vec3<f32>) -> vec3<f32> {
+    let e = vec2<f32>(1.0, -1.0) * 0.0005;
+    return normalize(
+        e.xyy * map(p + e.xyy).x + e.yyx * map(p + e.yyx).x +
+        e.yxy * map(p + e.yxy).x + e.xxx * map(p + e.xxx).x
+    );
+}
+
+// Light reaching `origin` from `direction`: the closer the ray passes by
+// an occluder relative to how far along it is, the darker; larger `k`
+// gives harder shadows
+fn soft_shadow(origin: vec3<f32>, direction: vec3<f32>, max_distance: f32, k: f32) -> f32 {
+    var light = 1.0;
+    var t = 0.02;
+    for (var i = 0; i < 96 && t < max_distance; i++) {
+        let h = map(origin + direction * t).x;
+        if h < 0.0001 {
+            return 0.0;
+        }
+        light = min(light, k * h / t);
+        t += clamp(h, 0.01, 0.5);
+    }
+    return clamp(light, 0.0, 1.0);
+}
+
+// How open the surface at `p` is: samples along the normal that are closer
+// to other surfaces than to `p` are occluded, nearer samples weigh more
+fn ambient_occlusion(p: vec3<f32>, normal: vec3<f32>, samples: i32, radius: f32) -> f32 {
+    var occlusion = 0.0;
+    var total = 0.0;
+    var weight = 1.0;
+    for (var i = 1; i <= samples; i++) {
+        let h = radius * f32(i) / f32(samples);
+        occlusion += weight * max(h - map(p + normal * h).x, 0.0);
+        total += weight * h;
+        weight *= 0.75;
+    }
+    return clamp(1.0 - occlusion / max(total, 1e-4), 0.0, 1.0);
+}
+
+fn checker(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    return 0.5 + 0.5 * f32((i32(cell.x) + i32(cell.y)) & 1);
+}
+
+// Blue to red ramp for step counts
+fn heat(t: f32) -> vec3<f32> {
+    let x = clamp(t, 0.0, 1.0);
+    return clamp(vec3<f32>(2.0 * x - 0.5, 1.0 - abs(2.0 * x - 1.0), 1.5 - 2.0 * x), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+fn sky(direction: vec3<f32>) -> vec3<f32> {
+    return mix(vec3<f32>(0.55, 0.6, 0.7), vec3<f32>(0.2, 0.35, 0.6), clamp(direction.y * 2.0, 0.0, 1.0));
+}
+"#;
+
+const PRIMITIVES_SOURCE: &str = r#"// Sphere traced primitives, blended with a smooth union
+// Drag on the preview to orbit; show_steps colors pixels by march steps
+
+struct Params {
+    blend: f32,
+    spin_speed: f32,
+    max_steps: i32,
+    show_steps: u32,
+}
+
+@group(1) @binding(0) var<uniform> params: Params;
+
+fn map(p: vec3<f32>) -> vec2<f32> {
+    let q = rotate_y(p, iTime * params.spin_speed);
+    var d = sd_sphere(q - vec3<f32>(-1.2, 0.0, 0.0), 0.6);
+    d = op_smooth_union(d, sd_box(q - vec3<f32>(1.2, 0.0, 0.0), vec3<f32>(0.45)), params.blend);
+    d = op_smooth_union(d, sd_torus(q - vec3<f32>(0.0, 0.0, 1.2), vec2<f32>(0.5, 0.18)), params.blend);
+    d = op_smooth_union(d, sd_capsule(q, vec3<f32>(0.0, -0.5, -1.2), vec3<f32>(0.0, 0.6, -1.2), 0.25), params.blend);
+    let ground = p.y + 1.0;
+    if ground < d {
+        return vec2<f32>(ground, 0.0);
+    }
+    return vec2<f32>(d, 1.0);
+}
+
+fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {
+    let angles = mouse_orbit();
+    let ray = orbit_camera(fragCoord, vec3<f32>(0.0), 5.0, angles.x, angles.y);
+    let hit = march(ray, params.max_steps, 40.0);
+    if params.show_steps == 1u {
+        return vec4<f32>(heat(f32(hit.steps) / f32(params.max_steps)), 1.0);
+    }
+
+    var color = sky(ray.direction);
+    if hit.material >= 0.0 {
+        let p = ray.origin + ray.direction * hit.distance;
+        let normal = normal_at(p);
+        let light = normalize(vec3<f32>(0.6, 0.8, 0.4));
+        var albedo = vec3<f32>(0.9, 0.45, 0.2);
+        if hit.material < 0.5 {
+            albedo = vec3<f32>(0.3) * checker(p.xz);
+        }
+        color = albedo * (0.15 + 0.85 * max(dot(normal, light), 0.0));
+        color = mix(color, sky(ray.direction), 1.0 - exp(-0.002 * hit.distance * hit.distance));
+    }
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+const SOFT_SHADOWS_SOURCE: &str = r#"// Soft shadows from the distance field: the shadow ray notes how close it
+// passes by occluders, which approximates the penumbra of an area light
+
+struct Params {
+    softness: f32,
+    light_height: f32,
+    light_speed: f32,
+    use_hard_shadows: u32,
+}
+
+@group(1) @binding(0) var<uniform> params: Params;
+
+fn map(p: vec3<f32>) -> vec2<f32> {
+    var d = sd_sphere(p - vec3<f32>(0.0, 0.0, 0.0), 0.7);
+    d = min(d, sd_round_box(p - vec3<f32>(1.6, -0.2, 0.3), vec3<f32>(0.4, 0.8, 0.4), 0.08));
+    d = min(d, sd_torus(p - vec3<f32>(-1.6, -0.45, 0.4), vec2<f32>(0.5, 0.15)));
+    d = min(d, sd_capsule(p, vec3<f32>(-0.4, -0.7, 1.5), vec3<f32>(0.6, 0.3, 1.8), 0.12));
+    let ground = p.y + 0.7;
+    if ground < d {
+        return vec2<f32>(ground, 0.0);
+    }
+    return vec2<f32>(d, 1.0);
+}
+
+fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {
+    let angles = mouse_orbit();
+    let ray = orbit_camera(fragCoord, vec3<f32>(0.0, -0.2, 0.0), 6.0, angles.x, angles.y);
+    let hit = march(ray, 160, 40.0);
+
+    var color = sky(ray.direction);
+    if hit.material >= 0.0 {
+        let p = ray.origin + ray.direction * hit.distance;
+        let normal = normal_at(p);
+        let angle = iTime * params.light_speed;
+        let light_position = vec3<f32>(3.0 * cos(angle), params.light_height, 3.0 * sin(angle));
+        let to_light = light_position - p;
+        let light = normalize(to_light);
+        var shadow = soft_shadow(p + normal * 0.002, light, length(to_light), params.softness);
+        if params.use_hard_shadows == 1u {
+            shadow = step(0.999, soft_shadow(p + normal * 0.002, light, length(to_light), 1e4));
+        }
+        var albedo = vec3<f32>(0.8, 0.75, 0.7);
+        if hit.material < 0.5 {
+            albedo = vec3<f32>(0.25 + 0.15 * checker(p.xz));
+        }
+        let diffuse = max(dot(normal, light), 0.0) * shadow;
+        color = albedo * (0.08 + vec3<f32>(1.0, 0.9, 0.75) * diffuse);
+    }
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+const AMBIENT_OCCLUSION_SOURCE: &str = r#"// Ambient occlusion from the distance field: a few samples along the
+// normal darken creases and corners where other surfaces are close
+
+struct Params {
+    strength: f32,
+    radius: f32,
+    samples: i32,
+    show_occlusion: u32,
+}
+
+@group(1) @binding(0) var<uniform> params: Params;
+
+fn map(p: vec3<f32>) -> vec2<f32> {
+    // A corner of a room with a pile of spheres and a step
+    let room = min(min(p.y + 1.0, p.x + 2.0), p.z + 2.0);
+    var d = sd_sphere(p - vec3<f32>(-1.3, -0.5, -1.3), 0.5);
+    d = min(d, sd_sphere(p - vec3<f32>(-0.45, -0.65, -1.35), 0.35));
+    d = min(d, sd_sphere(p - vec3<f32>(-1.35, -0.7, -0.5), 0.3));
+    d = min(d, sd_sphere(p - vec3<f32>(-1.2, 0.2, -1.2), 0.3));
+    d = min(d, sd_box(p - vec3<f32>(0.8, -0.8, -1.0), vec3<f32>(0.6, 0.2, 1.0)));
+    d = min(d, sd_torus(p - vec3<f32>(0.5, -0.9, 0.8), vec2<f32>(0.5, 0.1)));
+    if room < d {
+        return vec2<f32>(room, 0.0);
+    }
+    return vec2<f32>(d, 1.0);
+}
+
+fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {
+    let angles = mouse_orbit();
+    let yaw = clamp(angles.x, 0.1, 1.47);
+    let ray = orbit_camera(fragCoord, vec3<f32>(-0.6, -0.5, -0.6), 4.5, yaw, angles.y);
+    let hit = march(ray, 160, 40.0);
+
+    var color = sky(ray.direction);
+    if hit.material >= 0.0 {
+        let p = ray.origin + ray.direction * hit.distance;
+        let normal = normal_at(p);
+        let occlusion = ambient_occlusion(p, normal, params.samples, params.radius);
+        let ambient = mix(1.0, occlusion, clamp(params.strength, 0.0, 1.0));
+        if params.show_occlusion == 1u {
+            return vec4<f32>(vec3<f32>(ambient), 1.0);
+        }
+        var albedo = vec3<f32>(0.85, 0.8, 0.7);
+        if hit.material > 0.5 {
+            albedo = vec3<f32>(0.35, 0.55, 0.85);
+        }
+        // Light from the whole sky, so occlusion is most of the shading
+        let sky_light = 0.6 + 0.4 * normal.y;
+        color = albedo * sky_light * ambient;
+    }
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+const REPETITION_SOURCE: &str = r#"// Infinite repetition: folding space into one cell makes a single shape
+// fill the world for the price of one distance evaluation
+
+struct Params {
+    spacing: f32,
+    twist: f32,
+    fog_density: f32,
+    max_steps: i32,
+}
+
+@group(1) @binding(0) var<uniform> params: Params;
+
+fn map(p: vec3<f32>) -> vec2<f32> {
+    let spacing = max(params.spacing, 1.5);
+    let cell = round(p / spacing);
+    let local = p - spacing * cell;
+    // Every cell turns a little further
+    let q = rotate_y(local, params.twist * (cell.x + cell.z) + iTime * 0.5);
+    let d = sd_round_box(q, vec3<f32>(0.45, 0.45, 0.45), 0.1);
+    return vec2<f32>(d, fract(dot(cell, vec3<f32>(0.13, 0.37, 0.71))));
+}
+
+fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {
+    let angles = mouse_orbit();
+    // Fly down the gap between the rows of boxes
+    let gap = 0.5 * max(params.spacing, 1.5);
+    let ray = orbit_camera(fragCoord, vec3<f32>(gap, gap, -iTime), 0.01, angles.x, angles.y - 0.35);
+    let hit = march(ray, params.max_steps, 120.0);
+
+    let fog_color = vec3<f32>(0.05, 0.06, 0.1);
+    var color = fog_color;
+    if hit.material >= 0.0 {
+        let p = ray.origin + ray.direction * hit.distance;
+        let normal = normal_at(p);
+        let albedo = 0.5 + 0.5 * cos(6.2831853 * (hit.material + vec3<f32>(0.0, 0.33, 0.67)));
+        let light = normalize(vec3<f32>(0.5, 0.8, 0.3));
+        color = albedo * (0.2 + 0.8 * max(dot(normal, light), 0.0));
+        color = mix(fog_color, color, exp(-params.fog_density * hit.distance));
+    }
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+/// How a parameter is edited in the panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SdfParamKind {
+    /// Slider from the first value to the second
+    Range(f32, f32),
+    /// Checkbox writing 0 or 1
+    Toggle,
+}
+
+/// A field of an entry's `params` uniform
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfParam {
+    /// Field name in the shader
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: SdfParamKind,
+    /// Value the field starts at when the entry is loaded
+    pub default: f32,
+}
+
+impl SdfParam {
+    const fn range(
+        name: &'static str,
+        label: &'static str,
+        min: f32,
+        max: f32,
+        default: f32,
+    ) -> Self {
+        Self {
+            name,
+            label,
+            kind: SdfParamKind::Range(min, max),
+            default,
+        }
+    }
+
+    const fn toggle(name: &'static str, label: &'static str) -> Self {
+        Self {
+            name,
+            label,
+            kind: SdfParamKind::Toggle,
+            default: 0.0,
+        }
+    }
+}
+
+/// An entry of the gallery
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfExample {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The scene and `mainImage`, without the library
+    pub body: &'static str,
+    pub params: &'static [SdfParam],
+}
+
+impl SdfExample {
+    /// Playground source: the body followed by [`SDF_LIBRARY`]
+    pub fn source(&self) -> String {
+        format!("{}{}", self.body, SDF_LIBRARY)
+    }
+}
+
+/// Every entry of the gallery
+pub const SDF_GALLERY: &[SdfExample] = &[
+    SdfExample {
+        id: "primitives",
+        name: "Sphere Tracing Primitives",
+        description: "A sphere, box, torus and capsule blended with a smooth union. Show \
+                      the steps to see where the marcher works hardest: grazing rays \
+                      along the surfaces.",
+        body: PRIMITIVES_SOURCE,
+        params: &[
+            SdfParam::range("blend", "Blend", 0.0, 1.0, 0.3),
+            SdfParam::range("spin_speed", "Spin speed", 0.0, 2.0, 0.3),
+            SdfParam::range("max_steps", "Max steps", 8.0, 256.0, 128.0),
+            SdfParam::toggle("show_steps", "Show march steps"),
+        ],
+    },
+    SdfExample {
+        id: "soft_shadows",
+        name: "Soft Shadows",
+        description: "Penumbrae from one extra march towards the light, darker where the \
+                      shadow ray grazes an occluder. Compare with hard shadows.",
+        body: SOFT_SHADOWS_SOURCE,
+        params: &[
+            SdfParam::range("softness", "Hardness (k)", 1.0, 64.0, 8.0),
+            SdfParam::range("light_height", "Light height", 0.5, 5.0, 2.5),
+            SdfParam::range("light_speed", "Light speed", 0.0, 2.0, 0.4),
+            SdfParam::toggle("use_hard_shadows", "Hard shadows"),
+        ],
+    },
+    SdfExample {
+        id: "ambient_occlusion",
+        name: "Ambient Occlusion",
+        description: "A handful of distance samples along the normal darken corners and \
+                      contact points, under light from the whole sky.",
+        body: AMBIENT_OCCLUSION_SOURCE,
+        params: &[
+            SdfParam::range("strength", "Strength", 0.0, 1.0, 1.0),
+            SdfParam::range("radius", "Radius", 0.05, 1.0, 0.4),
+            SdfParam::range("samples", "Samples", 1.0, 16.0, 5.0),
+            SdfParam::toggle("show_occlusion", "Show occlusion only"),
+        ],
+    },
+    SdfExample {
+        id: "repetition",
+        name: "Infinite Repetition",
+        description: "One rounded box repeated through all of space by folding the \
+                      position into a cell, flown through with distance fog.",
+        body: REPETITION_SOURCE,
+        params: &[
+            SdfParam::range("spacing", "Spacing", 1.5, 6.0, 2.5),
+            SdfParam::range("twist", "Twist per cell", 0.0, 1.0, 0.3),
+            SdfParam::range("fog_density", "Fog density", 0.0, 0.3, 0.06),
+            SdfParam::range("max_steps", "Max steps", 8.0, 256.0, 160.0),
+        ],
+    },
+];
+
+/// Gallery entry with the given id
+pub fn find(id: &str) -> Option<&'static SdfExample> {
+    SDF_GALLERY.iter().find(|example| example.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fragment_playground::compose;
+    use crate::uniform_tweaker::UniformTweaker;
+
+    #[test]
+    fn test_gallery_shaders_validate() {
+        for example in SDF_GALLERY {
+            let source = compose(&example.source());
+            let module = naga::front::wgsl::parse_str(&source)
+                .unwrap_or_else(|e| panic!("{}: {}", example.id, e.emit_to_string(&source)));
+            naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::all(),
+            )
+            .validate(&module)
+            .unwrap_or_else(|e| panic!("{}: {}", example.id, e.emit_to_string(&source)));
+        }
+    }
+
+    #[test]
+    fn test_params_match_the_shaders() {
+        for example in SDF_GALLERY {
+            let tweaker = UniformTweaker::from_wgsl(&compose(&example.source())).unwrap();
+            let block = tweaker.block(1, 0).expect("params at @group(1)");
+            assert_eq!(block.fields.len(), example.params.len(), "{}", example.id);
+            for param in example.params {
+                assert!(
+                    block.field(param.name).is_some(),
+                    "{}.{}",
+                    example.id,
+                    param.name
+                );
+                if let SdfParamKind::Range(min, max) = param.kind {
+                    assert!((min..=max).contains(&param.default), "{}", param.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ids_are_unique() {
+        for (i, example) in SDF_GALLERY.iter().enumerate() {
+            assert_eq!(find(example.id), Some(&SDF_GALLERY[i]));
+        }
+        assert_eq!(find("missing"), None);
+    }
+}
@@ -1,29 +1,80 @@
+use crate::asset_cache::{format_bytes, AssetCache, CacheStats};
+use crate::asset_watcher::{AssetChangeEvent, AssetWatcher};
+use crate::assets;
+use crate::device_pipeline_cache::{DevicePipelineCache, PipelineCacheInfo};
+use crate::i18n::{self, tr, tr_args};
 use crate::implementation::WebGPUImplementation;
 use crate::state::Theme;
+use crate::theme::{self, ThemePalette};
 use crate::tooltip::TooltipExt;
+use std::path::PathBuf;
+
+/// Number of recent asset changes listed in the settings panel
+const MAX_RECENT_ASSET_CHANGES: usize = 10;
 
 /// UI panel for application settings
 pub struct SettingsPanel {
     /// Current theme selection
     current_theme: Theme,
+    /// Palette of the custom theme, edited while it is selected
+    custom_theme: ThemePalette,
     /// Currently selected backend
     selected_backend: WebGPUImplementation,
+    /// User assets directory text input
+    assets_dir_input: String,
+    /// Watcher for the effective assets directory
+    asset_watcher: Option<AssetWatcher>,
+    /// Most recent asset changes, newest first
+    recent_asset_changes: Vec<AssetChangeEvent>,
+    /// Result of the last assets directory change
+    assets_message: Option<String>,
+    /// Whether the assets directory changed since the last check
+    assets_dir_changed: bool,
+    /// Processed asset cache, opened when the settings are first shown
+    asset_cache: Option<AssetCache>,
+    /// Cache usage, refreshed on demand
+    cache_stats: Option<CacheStats>,
+    /// Cache size limit text input in MiB
+    cache_limit_input: String,
+    /// Result of the last cache operation
+    cache_message: Option<String>,
+    /// Driver pipeline cache size, refreshed on demand
+    pipeline_cache_info: Option<PipelineCacheInfo>,
+    /// Result of the last driver pipeline cache operation
+    pipeline_cache_message: Option<String>,
+    /// Whether the UI language changed since the last check
+    language_changed: bool,
+    /// Result of the last translation reload
+    language_message: Option<String>,
 }
 
 impl SettingsPanel {
     /// Create a new settings panel with default theme
     pub fn new() -> Self {
-        Self {
-            current_theme: Theme::default(),
-            selected_backend: WebGPUImplementation::current(),
-        }
+        Self::with_theme(Theme::default())
     }
 
     /// Create a settings panel with a specific theme
     pub fn with_theme(theme: Theme) -> Self {
         Self {
             current_theme: theme,
+            custom_theme: ThemePalette::default(),
             selected_backend: WebGPUImplementation::current(),
+            assets_dir_input: assets::user_assets_dir()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            asset_watcher: None,
+            recent_asset_changes: Vec::new(),
+            assets_message: None,
+            assets_dir_changed: false,
+            asset_cache: None,
+            cache_stats: None,
+            cache_limit_input: String::new(),
+            cache_message: None,
+            pipeline_cache_info: None,
+            pipeline_cache_message: None,
+            language_changed: false,
+            language_message: None,
         }
     }
 
@@ -37,52 +88,550 @@ impl SettingsPanel {
         self.current_theme = theme;
     }
 
+    /// Palette of the custom theme
+    pub fn custom_theme(&self) -> &ThemePalette {
+        &self.custom_theme
+    }
+
+    /// Replace the palette of the custom theme
+    pub fn set_custom_theme(&mut self, palette: ThemePalette) {
+        self.custom_theme = palette;
+    }
+
+    /// Palette of the current theme
+    pub fn palette(&self) -> ThemePalette {
+        ThemePalette::for_theme(self.current_theme, &self.custom_theme)
+    }
+
+    /// Returns true once after the UI language was changed
+    pub fn take_language_changed(&mut self) -> bool {
+        std::mem::take(&mut self.language_changed)
+    }
+
+    /// Use a user assets directory, or the built-in one when `None`
+    ///
+    /// Restarts the asset watcher so that it follows the new directory.
+    pub fn apply_assets_dir(&mut self, dir: Option<PathBuf>) -> Result<(), String> {
+        if let Some(dir) = &dir {
+            if !dir.is_dir() {
+                return Err(tr_args(
+                    "settings-assets-not-a-directory",
+                    &[("path", &dir.display())],
+                ));
+            }
+        }
+        assets::set_user_assets_dir(dir);
+        self.asset_watcher = None;
+        self.recent_asset_changes.clear();
+        self.assets_dir_changed = true;
+        self.start_asset_watcher();
+        Ok(())
+    }
+
+    fn start_asset_watcher(&mut self) {
+        match AssetWatcher::new() {
+            Ok(watcher) => self.asset_watcher = Some(watcher),
+            Err(e) => log::warn!("Failed to watch assets directory: {}", e),
+        }
+    }
+
+    /// Collect asset changes detected since the last call
+    ///
+    /// Starts watching the effective assets directory on first use.
+    pub fn poll_asset_changes(&mut self) -> Vec<AssetChangeEvent> {
+        if self.asset_watcher.is_none() {
+            self.start_asset_watcher();
+        }
+        let events = self
+            .asset_watcher
+            .as_ref()
+            .map(AssetWatcher::poll_all)
+            .unwrap_or_default();
+        for event in &events {
+            self.recent_asset_changes.insert(0, event.clone());
+        }
+        self.recent_asset_changes.truncate(MAX_RECENT_ASSET_CHANGES);
+        events
+    }
+
+    /// Returns true once after the assets directory was changed
+    pub fn take_assets_dir_changed(&mut self) -> bool {
+        std::mem::take(&mut self.assets_dir_changed)
+    }
+
+    fn render_language_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("settings-language-heading"));
+        ui.label(tr("settings-language-help"));
+        ui.add_space(5.0);
+
+        let languages = i18n::languages();
+        let current = i18n::language();
+        let mut selected = current.clone();
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label(tr("settings-language-label"))
+                .selected_text(
+                    languages
+                        .iter()
+                        .find(|language| language.code == current)
+                        .map(|language| language.name.clone())
+                        .unwrap_or_else(|| current.clone()),
+                )
+                .show_ui(ui, |ui| {
+                    for language in &languages {
+                        ui.selectable_value(&mut selected, language.code.clone(), &language.name)
+                            .on_hover_text(tr_args(
+                                "settings-language-coverage",
+                                &[("percent", &(language.coverage * 100.0).round())],
+                            ));
+                    }
+                });
+            if ui.button(tr("settings-language-reload")).clicked() {
+                self.language_message = Some(match i18n::reload_community_translations() {
+                    Ok(count) => tr_args("settings-language-reloaded", &[("count", &count)]),
+                    Err(errors) => format!("✗ {}", errors.join("\n")),
+                });
+            }
+        });
+        if let Some(language) = languages.iter().find(|language| language.code == current) {
+            if language.coverage < 1.0 {
+                ui.add(egui::ProgressBar::new(language.coverage).text(tr_args(
+                    "settings-language-coverage",
+                    &[("percent", &(language.coverage * 100.0).round())],
+                )));
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-location"));
+            ui.code(i18n::community_dir().display().to_string());
+        });
+        if let Some(msg) = &self.language_message {
+            let color = if msg.starts_with('✓') {
+                egui::Color32::from_rgb(100, 200, 100)
+            } else {
+                egui::Color32::from_rgb(220, 80, 80)
+            };
+            ui.colored_label(color, msg);
+        }
+
+        if selected != current && i18n::set_language(&selected) {
+            self.language_changed = true;
+        }
+    }
+
+    /// Editor of the custom theme's palette; true if it changed
+    fn render_custom_theme_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let palette = &mut self.custom_theme;
+        let previous = palette.clone();
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-theme-start-from"));
+            if ui.button(tr("settings-theme-dark")).clicked() {
+                *palette = ThemePalette::dark();
+            }
+            if ui.button(tr("settings-theme-light")).clicked() {
+                *palette = ThemePalette::light();
+            }
+        });
+        egui::Grid::new("custom_theme_grid")
+            .num_columns(2)
+            .spacing([20.0, 6.0])
+            .show(ui, |ui| {
+                ui.label(tr("settings-theme-base"));
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut palette.dark, true, tr("settings-theme-dark"));
+                    ui.radio_value(&mut palette.dark, false, tr("settings-theme-light"));
+                });
+                ui.end_row();
+
+                ui.label(tr("settings-theme-panel-fill"));
+                ui.color_edit_button_srgb(&mut palette.panel_fill);
+                ui.end_row();
+
+                ui.label(tr("settings-theme-window-fill"));
+                ui.color_edit_button_srgb(&mut palette.window_fill);
+                ui.end_row();
+
+                ui.label(tr("settings-theme-accent"));
+                ui.color_edit_button_srgb(&mut palette.accent);
+                ui.end_row();
+
+                ui.label(tr("settings-theme-text"));
+                ui.horizontal(|ui| {
+                    let mut custom_text = palette.text.is_some();
+                    if ui
+                        .checkbox(&mut custom_text, tr("settings-theme-text-override"))
+                        .changed()
+                    {
+                        palette.text = custom_text.then(|| {
+                            let color = palette.visuals().text_color();
+                            [color.r(), color.g(), color.b()]
+                        });
+                    }
+                    if let Some(text) = &mut palette.text {
+                        ui.color_edit_button_srgb(text);
+                    }
+                });
+                ui.end_row();
+
+                ui.label(tr("settings-theme-preview-clear"));
+                ui.color_edit_button_rgba_unmultiplied(&mut palette.preview_clear);
+                ui.end_row();
+
+                ui.label(tr("settings-theme-backdrop"));
+                ui.horizontal(|ui| {
+                    theme::backdrop_selector(ui, "custom_theme_backdrop", &mut palette.backdrop);
+                    for color in &mut palette.backdrop_colors {
+                        ui.color_edit_button_srgb(color);
+                    }
+                    ui.add(
+                        egui::DragValue::new(&mut palette.backdrop_cell)
+                            .range(2.0..=64.0)
+                            .suffix(" pt"),
+                    );
+                });
+                ui.end_row();
+            });
+
+        let (response, painter) =
+            ui.allocate_painter(egui::vec2(240.0, 48.0), egui::Sense::hover());
+        palette.paint_backdrop(&painter, response.rect);
+        let [r, g, b, a] = palette
+            .preview_clear
+            .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        painter.rect_filled(
+            response.rect.shrink(12.0),
+            0.0,
+            egui::Color32::from_rgba_unmultiplied(r, g, b, a),
+        );
+        response.on_hover_text(tr("settings-theme-backdrop-preview"));
+
+        *palette != previous
+    }
+
+    fn render_assets_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("settings-assets-heading"));
+        ui.label(tr("settings-assets-help"));
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-assets-directory"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.assets_dir_input)
+                    .hint_text("/path/to/my_assets")
+                    .desired_width(300.0),
+            );
+            if ui.button(tr("settings-apply")).clicked() {
+                let input = self.assets_dir_input.trim();
+                let dir = (!input.is_empty()).then(|| PathBuf::from(input));
+                self.assets_message = Some(match self.apply_assets_dir(dir) {
+                    Ok(()) => tr_args(
+                        "settings-assets-using",
+                        &[("path", &assets::assets_dir().display())],
+                    ),
+                    Err(e) => format!("✗ {}", e),
+                });
+            }
+            if ui.button(tr("settings-assets-use-builtin")).clicked() {
+                self.assets_dir_input.clear();
+                self.assets_message = Some(match self.apply_assets_dir(None) {
+                    Ok(()) => tr("settings-assets-using-builtin"),
+                    Err(e) => format!("✗ {}", e),
+                });
+            }
+        });
+
+        if let Some(msg) = &self.assets_message {
+            let color = if msg.starts_with('✓') {
+                egui::Color32::from_rgb(100, 200, 100)
+            } else {
+                egui::Color32::from_rgb(220, 80, 80)
+            };
+            ui.colored_label(color, msg);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-assets-effective"));
+            ui.code(assets::assets_dir().display().to_string());
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-env-variable"));
+            ui.code(assets::ASSETS_DIR_ENV);
+        });
+
+        if !self.recent_asset_changes.is_empty() {
+            ui.add_space(5.0);
+            ui.label(tr("settings-assets-recent"));
+            for event in &self.recent_asset_changes {
+                ui.label(format!(
+                    "  {:?} {}/{}",
+                    event.change,
+                    event.asset.subdir(),
+                    event.filename
+                ));
+            }
+        }
+    }
+
+    fn refresh_cache_stats(&mut self) {
+        if self.asset_cache.is_none() {
+            match AssetCache::open_default() {
+                Ok(cache) => {
+                    self.cache_limit_input = (cache.max_bytes() / (1024 * 1024)).to_string();
+                    self.asset_cache = Some(cache);
+                }
+                Err(e) => {
+                    self.cache_message =
+                        Some(tr_args("settings-cache-open-failed", &[("error", &e)]));
+                    return;
+                }
+            }
+        }
+        if let Some(cache) = &self.asset_cache {
+            match cache.stats() {
+                Ok(stats) => self.cache_stats = Some(stats),
+                Err(e) => self.cache_message = Some(format!("✗ {}", e)),
+            }
+        }
+    }
+
+    fn render_cache_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("settings-cache-heading"));
+        ui.label(tr("settings-cache-help"));
+        ui.add_space(5.0);
+
+        if self.cache_stats.is_none() && self.cache_message.is_none() {
+            self.refresh_cache_stats();
+        }
+        let Some(cache) = &mut self.asset_cache else {
+            if let Some(msg) = &self.cache_message {
+                ui.colored_label(egui::Color32::from_rgb(220, 80, 80), msg);
+            }
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-location"));
+            ui.code(cache.root().display().to_string());
+        });
+
+        if let Some(stats) = &self.cache_stats {
+            let fraction = if cache.max_bytes() == 0 {
+                1.0
+            } else {
+                stats.total_bytes as f32 / cache.max_bytes() as f32
+            };
+            ui.add(egui::ProgressBar::new(fraction.min(1.0)).text(tr_args(
+                "settings-cache-usage",
+                &[
+                    ("used", &format_bytes(stats.total_bytes)),
+                    ("limit", &format_bytes(cache.max_bytes())),
+                    ("entries", &stats.entries),
+                ],
+            )));
+
+            if !stats.per_kind.is_empty() {
+                egui::Grid::new("asset_cache_kinds")
+                    .num_columns(3)
+                    .spacing([20.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (kind, (count, bytes)) in &stats.per_kind {
+                            ui.label(kind);
+                            ui.label(tr_args("settings-cache-entries", &[("count", count)]));
+                            ui.label(format_bytes(*bytes));
+                            ui.end_row();
+                        }
+                    });
+            }
+        }
+
+        let mut refresh = false;
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-cache-limit"));
+            ui.add(egui::TextEdit::singleline(&mut self.cache_limit_input).desired_width(80.0));
+            if ui.button(tr("settings-apply")).clicked() {
+                self.cache_message = Some(match self.cache_limit_input.trim().parse::<u64>() {
+                    Ok(mib) => match cache.set_max_bytes(mib * 1024 * 1024) {
+                        Ok(()) => tr_args("settings-cache-limit-set", &[("mib", &mib)]),
+                        Err(e) => format!("✗ {}", e),
+                    },
+                    Err(_) => tr("settings-cache-limit-invalid"),
+                });
+                refresh = true;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("settings-refresh")).clicked() {
+                refresh = true;
+            }
+            if ui.button(tr("settings-clear-cache")).clicked() {
+                self.cache_message = Some(match cache.clear() {
+                    Ok(()) => tr("settings-cache-cleared"),
+                    Err(e) => format!("✗ {}", e),
+                });
+                refresh = true;
+            }
+        });
+
+        if let Some(msg) = &self.cache_message {
+            let color = if msg.starts_with('✓') {
+                egui::Color32::from_rgb(100, 200, 100)
+            } else {
+                egui::Color32::from_rgb(220, 80, 80)
+            };
+            ui.colored_label(color, msg);
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-cache-env-variable"));
+            ui.code(crate::asset_cache::CACHE_DIR_ENV);
+        });
+
+        if refresh {
+            self.refresh_cache_stats();
+        }
+    }
+
+    fn render_pipeline_cache_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("settings-pipeline-cache-heading"));
+        ui.label(tr("settings-pipeline-cache-help"));
+        ui.add_space(5.0);
+
+        let cache = DevicePipelineCache::global();
+        if self.pipeline_cache_info.is_none() {
+            self.pipeline_cache_info = cache.info();
+        }
+        let Some(info) = &self.pipeline_cache_info else {
+            ui.label(tr("settings-pipeline-cache-unavailable"));
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-location"));
+            ui.code(info.path.display().to_string());
+        });
+        ui.label(tr_args(
+            "settings-pipeline-cache-loaded",
+            &[("size", &format_bytes(info.loaded_bytes as u64))],
+        ));
+        ui.label(tr_args(
+            "settings-pipeline-cache-size",
+            &[("size", &format_bytes(info.data_bytes as u64))],
+        ));
+
+        let mut refresh = false;
+        ui.horizontal(|ui| {
+            if ui.button(tr("settings-refresh")).clicked() {
+                refresh = true;
+            }
+            if ui.button(tr("settings-pipeline-cache-save")).clicked() {
+                self.pipeline_cache_message = Some(match cache.save() {
+                    Ok(bytes) => tr_args(
+                        "settings-pipeline-cache-saved",
+                        &[("size", &format_bytes(bytes as u64))],
+                    ),
+                    Err(e) => format!("✗ {}", e),
+                });
+                refresh = true;
+            }
+            if ui.button(tr("settings-clear-cache")).clicked() {
+                self.pipeline_cache_message = Some(match cache.clear() {
+                    Ok(()) => tr("settings-pipeline-cache-cleared"),
+                    Err(e) => format!("✗ {}", e),
+                });
+                refresh = true;
+            }
+        });
+
+        if let Some(msg) = &self.pipeline_cache_message {
+            let color = if msg.starts_with('✓') {
+                egui::Color32::from_rgb(100, 200, 100)
+            } else {
+                egui::Color32::from_rgb(220, 80, 80)
+            };
+            ui.colored_label(color, msg);
+        }
+
+        if refresh {
+            self.pipeline_cache_info = cache.info();
+        }
+    }
+
     /// Render the settings panel UI
     pub fn ui(&mut self, ui: &mut egui::Ui) -> Option<Theme> {
         let mut theme_changed = None;
 
-        ui.heading("⚙️ Settings");
+        ui.heading(tr("settings-heading"));
+        ui.separator();
+        ui.add_space(10.0);
+
+        // Language Settings
+        self.render_language_ui(ui);
+
+        ui.add_space(20.0);
         ui.separator();
         ui.add_space(10.0);
 
         // Theme Settings
-        ui.heading("Theme");
-        ui.label("Choose your preferred UI theme:");
+        ui.heading(tr("settings-theme-heading"));
+        ui.label(tr("settings-theme-prompt"));
         ui.add_space(5.0);
 
         let previous_theme = self.current_theme;
 
-        egui::ComboBox::from_label("Theme")
-            .selected_text(match self.current_theme {
-                Theme::Light => "☀️ Light",
-                Theme::Dark => "🌙 Dark",
-            })
+        egui::ComboBox::from_label(tr("settings-theme-label"))
+            .selected_text(theme_name(self.current_theme))
             .show_ui(ui, |ui| {
-                ui.selectable_value(&mut self.current_theme, Theme::Light, "☀️ Light");
-                ui.selectable_value(&mut self.current_theme, Theme::Dark, "🌙 Dark");
+                for theme in [Theme::Light, Theme::Dark, Theme::Custom] {
+                    ui.selectable_value(&mut self.current_theme, theme, theme_name(theme));
+                }
             });
 
         if self.current_theme != previous_theme {
             theme_changed = Some(self.current_theme);
         }
+        if self.current_theme == Theme::Custom && self.render_custom_theme_ui(ui) {
+            theme_changed = Some(Theme::Custom);
+        }
 
         ui.add_space(10.0);
-        ui.label("Theme changes are applied immediately and saved automatically.");
+        ui.label(tr("settings-theme-help"));
+
+        ui.add_space(20.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        // Assets Settings
+        self.render_assets_ui(ui);
+
+        ui.add_space(20.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        // Asset Cache Settings
+        self.render_cache_ui(ui);
+
+        ui.add_space(20.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        // Driver Pipeline Cache Settings
+        self.render_pipeline_cache_ui(ui);
 
         ui.add_space(20.0);
         ui.separator();
         ui.add_space(10.0);
 
         // Backend Settings
-        ui.heading("🔧 WebGPU Backend");
-        ui.label("Select the WebGPU implementation backend:");
+        ui.heading(tr("settings-backend-heading"));
+        ui.label(tr("settings-backend-prompt"));
         ui.add_space(5.0);
 
         let current_backend = WebGPUImplementation::current();
 
         // Display current backend prominently
         ui.horizontal(|ui| {
-            ui.label("Current Backend:");
+            ui.label(tr("settings-backend-current"));
             match current_backend {
                 WebGPUImplementation::Wgpu => {
                     ui.colored_label(egui::Color32::from_rgb(100, 150, 255), "wgpu-rs");
@@ -97,24 +646,21 @@ impl SettingsPanel {
 
         // Backend selection dropdown
         let selected_text = match self.selected_backend {
-            WebGPUImplementation::Wgpu => "🦀 wgpu-rs (Rust implementation)",
+            WebGPUImplementation::Wgpu => tr("settings-backend-wgpu"),
             #[cfg(feature = "dawn")]
-            WebGPUImplementation::Dawn => "🌅 Dawn Native (C++ implementation)",
+            WebGPUImplementation::Dawn => tr("settings-backend-dawn"),
         };
 
-        egui::ComboBox::from_label("Select Backend")
+        egui::ComboBox::from_label(tr("settings-backend-select"))
             .selected_text(selected_text)
             .show_ui(ui, |ui| {
                 // Always show wgpu option
                 ui.selectable_value(
                     &mut self.selected_backend,
                     WebGPUImplementation::Wgpu,
-                    "🦀 wgpu-rs (Rust implementation)",
+                    tr("settings-backend-wgpu"),
                 )
-                .webgpu_tooltip(
-                    "wgpu-rs is a Rust implementation of the WebGPU API. Used by Firefox and Servo, it provides a fully featured and stable WebGPU implementation with excellent support for Vulkan, Metal, DirectX 12, and OpenGL backends.",
-                    None
-                );
+                .webgpu_tooltip(&tr("settings-backend-wgpu-tooltip"), None);
 
                 // Show Dawn option only if available
                 #[cfg(feature = "dawn")]
@@ -122,22 +668,19 @@ impl SettingsPanel {
                     ui.selectable_value(
                         &mut self.selected_backend,
                         WebGPUImplementation::Dawn,
-                        "🌅 Dawn Native (C++ implementation)",
+                        tr("settings-backend-dawn"),
                     )
-                    .webgpu_tooltip(
-                        "Dawn is Google's C++ implementation of the WebGPU API. Used by Chromium-based browsers (Chrome, Edge, Opera), it provides native WebGPU support with backends for Vulkan, Metal, and DirectX 12.",
-                        None
-                    );
+                    .webgpu_tooltip(&tr("settings-backend-dawn-tooltip"), None);
                 }
 
                 // Show Dawn as disabled if not compiled in
                 #[cfg(not(feature = "dawn"))]
                 {
-                    ui.add_enabled(false, egui::Label::new("🌅 Dawn Native (Not Available)"))
-                        .webgpu_tooltip(
-                            "Dawn is not available in this build. Compile with --features dawn to enable Google's C++ implementation of the WebGPU API used by Chromium browsers.",
-                            None
-                        );
+                    ui.add_enabled(
+                        false,
+                        egui::Label::new(tr("settings-backend-dawn-unavailable")),
+                    )
+                    .webgpu_tooltip(&tr("settings-backend-dawn-unavailable-tooltip"), None);
                 }
             });
 
@@ -152,15 +695,15 @@ impl SettingsPanel {
             ui.add_space(5.0);
             ui.colored_label(
                 egui::Color32::from_rgb(255, 200, 100),
-                "⚠️ Warning: Backend switching requires application restart",
+                tr("settings-backend-restart-warning"),
             );
             ui.add_space(5.0);
 
             ui.horizontal(|ui| {
-                ui.label("To apply this change, set the environment variable:");
+                ui.label(tr("settings-backend-set-variable"));
                 ui.code(format!("WEBGPU_IMPL={}", self.selected_backend.name()));
             });
-            ui.label("Then restart the application.");
+            ui.label(tr("settings-backend-restart"));
         }
 
         ui.add_space(10.0);
@@ -169,12 +712,12 @@ impl SettingsPanel {
         if WebGPUImplementation::is_dawn_available() {
             ui.colored_label(
                 egui::Color32::from_rgb(100, 200, 100),
-                "✓ Dawn support is compiled in",
+                tr("settings-backend-dawn-compiled"),
             );
         } else {
             ui.colored_label(
                 egui::Color32::from_rgb(200, 200, 100),
-                "ℹ️ Dawn support not available (compile with --features dawn)",
+                tr("settings-backend-dawn-missing"),
             );
         }
 
@@ -182,7 +725,7 @@ impl SettingsPanel {
 
         // Additional info
         let available_backends = WebGPUImplementation::available_implementations();
-        ui.label("💡 Tip: Available backends:");
+        ui.label(tr("settings-backend-available"));
         for backend in &available_backends {
             ui.horizontal(|ui| {
                 if *backend == current_backend {
@@ -194,9 +737,9 @@ impl SettingsPanel {
                 ui.label(format!(
                     "({})",
                     if *backend == current_backend {
-                        "active"
+                        tr("settings-backend-active")
                     } else {
-                        "inactive"
+                        tr("settings-backend-inactive")
                     }
                 ));
             });
@@ -206,6 +749,15 @@ impl SettingsPanel {
     }
 }
 
+/// Display name of `theme` in the current language
+fn theme_name(theme: Theme) -> String {
+    match theme {
+        Theme::Light => tr("settings-theme-light"),
+        Theme::Dark => tr("settings-theme-dark"),
+        Theme::Custom => tr("settings-theme-custom"),
+    }
+}
+
 impl Default for SettingsPanel {
     fn default() -> Self {
         Self::new()
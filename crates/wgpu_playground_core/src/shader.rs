@@ -1,4 +1,9 @@
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use crate::shader_include::{has_includes, IncludeResolver};
 
 /// Shader source can come from either a file or an inline string
 #[derive(Debug, Clone)]
@@ -90,18 +95,18 @@ impl ShaderModule {
                     ));
                 }
                 log::trace!("Using inline shader source ({} bytes)", code.len());
-                code.clone()
+                expand_includes(code.clone(), None)?
             }
             ShaderSource::File(filename) => {
                 log::debug!("Loading shader from file: {}", filename);
-                match crate::assets::load_shader(filename) {
+                match load_file(filename) {
                     Ok(code) => {
                         log::trace!("Loaded shader from file ({} bytes)", code.len());
                         code
                     }
                     Err(e) => {
                         log::error!("Failed to load shader from file '{}': {}", filename, e);
-                        return Err(e.into());
+                        return Err(e);
                     }
                 }
             }
@@ -159,6 +164,15 @@ impl ShaderModule {
         self.label.as_deref()
     }
 
+    /// Hash of the source code, which changes whenever the shader does
+    ///
+    /// Stable within a process; used to key compiled pipelines.
+    pub fn source_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.source.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Create a wgpu shader module from this shader
     ///
     /// This method compiles and validates the shader. If the shader contains
@@ -213,7 +227,7 @@ impl ShaderModule {
         match &self.source_type {
             ShaderSource::File(filename) => {
                 log::info!("Reloading shader from file: {}", filename);
-                match crate::assets::load_shader(filename) {
+                match load_file(filename) {
                     Ok(new_source) => {
                         if new_source.trim().is_empty() {
                             log::error!("Reloaded shader source is empty");
@@ -236,7 +250,7 @@ impl ShaderModule {
                     }
                     Err(e) => {
                         log::error!("Failed to reload shader from file '{}': {}", filename, e);
-                        Err(e.into())
+                        Err(e)
                     }
                 }
             }
@@ -248,6 +262,24 @@ impl ShaderModule {
     }
 }
 
+/// Load a shader from the shaders directory with its includes expanded
+fn load_file(filename: &str) -> Result<String, ShaderError> {
+    let path = crate::assets::resolve(crate::assets::AssetKind::Shader, filename)?;
+    let code = crate::assets::load_string_from_path(&path)?;
+    expand_includes(code, Some(&path))
+}
+
+/// Paste in the files `source` includes, looking in the shaders directories
+fn expand_includes(source: String, path: Option<&Path>) -> Result<String, ShaderError> {
+    if !has_includes(&source) {
+        return Ok(source);
+    }
+    IncludeResolver::with_shader_dirs()
+        .expand(&source, path)
+        .map(|expanded| expanded.source)
+        .map_err(|e| ShaderError::InvalidSource(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,6 +348,26 @@ mod tests {
         assert!(shader.is_ok());
     }
 
+    #[test]
+    fn test_source_hash_follows_the_source() {
+        let a = ShaderModule::from_source("@vertex fn main() {}", Some("a")).unwrap();
+        let b = ShaderModule::from_source("@vertex fn main() {}", Some("b")).unwrap();
+        let c = ShaderModule::from_source("@vertex fn other() {}", Some("a")).unwrap();
+        assert_eq!(a.source_hash(), b.source_hash());
+        assert_ne!(a.source_hash(), c.source_hash());
+    }
+
+    #[test]
+    fn test_inline_source_includes_shader_files() {
+        let source = "#include \"post_processing_common.wgsl\"\n@fragment fn fs() {}\n";
+        let shader = ShaderModule::from_source(source, None).unwrap();
+        assert!(shader.source().contains("fn vs_main"));
+        assert!(shader.source().ends_with("@fragment fn fs() {}\n"));
+
+        let missing = ShaderModule::from_source("#include \"missing.wgsl\"\n", None);
+        assert!(matches!(missing, Err(ShaderError::InvalidSource(_))));
+    }
+
     // Note: File loading tests would require the actual file to exist
     // Integration test in a separate file can test this with the example.wgsl file
 }
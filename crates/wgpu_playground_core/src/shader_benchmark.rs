@@ -0,0 +1,582 @@
+//! Shader compile-time benchmarking
+//!
+//! Compiling a WGSL shader happens in two stages: naga parses and validates
+//! the source (the front end), then the device turns the module into a
+//! driver shader, which for most backends only fully happens when a pipeline
+//! is created (the back end). [`benchmark_compile`] times both stages over a
+//! number of iterations. The first iteration is reported separately as the
+//! cold compile; later iterations show warm behaviour, including any
+//! driver-side caching.
+//!
+//! The pipeline is derived from the shader's own entry points: the first
+//! vertex/fragment pair becomes a render pipeline with vertex buffers and
+//! color targets matching its interface, otherwise the first compute entry
+//! point becomes a compute pipeline.
+
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use naga::{Binding, Module, ScalarKind, ShaderStage, TypeInner, VectorSize};
+use std::time::{Duration, Instant};
+
+/// Default number of iterations per benchmark
+pub const DEFAULT_ITERATIONS: usize = 20;
+
+/// Upper bound on iterations, to keep the UI responsive
+pub const MAX_ITERATIONS: usize = 1000;
+
+/// Benchmark settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkConfig {
+    /// Number of compiles, including the cold one
+    pub iterations: usize,
+    /// Also create a pipeline from each compiled module
+    pub create_pipeline: bool,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            iterations: DEFAULT_ITERATIONS,
+            create_pipeline: true,
+        }
+    }
+}
+
+/// Summary statistics of a set of timings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingStats {
+    pub count: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+}
+
+impl TimingStats {
+    /// Compute statistics, or `None` for an empty sample set
+    pub fn from_samples(samples: &[Duration]) -> Option<TimingStats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let mean_secs = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        let median = if count % 2 == 0 {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+        Some(TimingStats {
+            count,
+            min: sorted[0],
+            max: sorted[count - 1],
+            mean: Duration::from_secs_f64(mean_secs),
+            median,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Timings of one compile stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    /// First iteration
+    pub cold: Duration,
+    /// Remaining iterations, if there were any
+    pub warm: Option<TimingStats>,
+}
+
+impl PhaseTiming {
+    fn from_samples(samples: &[Duration]) -> Option<PhaseTiming> {
+        let (cold, warm) = samples.split_first()?;
+        Some(PhaseTiming {
+            cold: *cold,
+            warm: TimingStats::from_samples(warm),
+        })
+    }
+}
+
+/// Pipeline created from the shader's entry points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineTarget {
+    Render {
+        vertex: String,
+        fragment: Option<String>,
+    },
+    Compute {
+        entry_point: String,
+    },
+}
+
+impl std::fmt::Display for PipelineTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineTarget::Render {
+                vertex,
+                fragment: Some(fragment),
+            } => write!(f, "render pipeline ({} + {})", vertex, fragment),
+            PipelineTarget::Render {
+                vertex,
+                fragment: None,
+            } => write!(f, "render pipeline ({}, no fragment stage)", vertex),
+            PipelineTarget::Compute { entry_point } => {
+                write!(f, "compute pipeline ({})", entry_point)
+            }
+        }
+    }
+}
+
+/// Result of [`benchmark_compile`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkReport {
+    pub iterations: usize,
+    /// naga parse + validation
+    pub front_end: PhaseTiming,
+    /// `Device::create_shader_module`
+    pub shader_module: PhaseTiming,
+    /// Pipeline creation, where the driver compiles the shader
+    pub pipeline: Option<PhaseTiming>,
+    /// Pipeline that was benchmarked
+    pub pipeline_target: Option<PipelineTarget>,
+}
+
+impl BenchmarkReport {
+    /// Total cold compile time (front end, module and pipeline)
+    pub fn total_cold(&self) -> Duration {
+        self.front_end.cold
+            + self.shader_module.cold
+            + self.pipeline.as_ref().map_or(Duration::ZERO, |p| p.cold)
+    }
+}
+
+/// Parse and validate the source, returning the module
+fn front_end(source: &str) -> Result<Module, String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+    Validator::new(ValidationFlags::all(), Capabilities::default())
+        .validate(&module)
+        .map_err(|e| e.emit_to_string(source))?;
+    Ok(module)
+}
+
+/// Time naga's front end only; needs no device
+pub fn benchmark_front_end(source: &str, iterations: usize) -> Result<PhaseTiming, String> {
+    let iterations = iterations.clamp(1, MAX_ITERATIONS);
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        front_end(source)?;
+        samples.push(start.elapsed());
+    }
+    Ok(PhaseTiming::from_samples(&samples).expect("at least one iteration"))
+}
+
+/// Pick the pipeline to benchmark from the module's entry points
+pub fn pipeline_target(module: &Module) -> Option<PipelineTarget> {
+    let first = |stage: ShaderStage| {
+        module
+            .entry_points
+            .iter()
+            .find(|ep| ep.stage == stage)
+            .map(|ep| ep.name.clone())
+    };
+    if let Some(vertex) = first(ShaderStage::Vertex) {
+        return Some(PipelineTarget::Render {
+            vertex,
+            fragment: first(ShaderStage::Fragment),
+        });
+    }
+    first(ShaderStage::Compute).map(|entry_point| PipelineTarget::Compute { entry_point })
+}
+
+/// Location-bound types of an entry point's inputs or outputs
+fn located_types(
+    module: &Module,
+    bindings: impl Iterator<Item = (Option<Binding>, naga::Handle<naga::Type>)>,
+) -> Vec<(u32, TypeInner)> {
+    let mut located = Vec::new();
+    for (binding, ty) in bindings {
+        match binding {
+            Some(Binding::Location { location, .. }) => {
+                located.push((location, module.types[ty].inner.clone()))
+            }
+            Some(_) => {}
+            None => {
+                if let TypeInner::Struct { members, .. } = &module.types[ty].inner {
+                    for member in members {
+                        if let Some(Binding::Location { location, .. }) = member.binding {
+                            located.push((location, module.types[member.ty].inner.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    located.sort_by_key(|(location, _)| *location);
+    located
+}
+
+fn vector_parts(inner: &TypeInner) -> Option<(naga::Scalar, u32)> {
+    match *inner {
+        TypeInner::Scalar(scalar) => Some((scalar, 1)),
+        TypeInner::Vector { size, scalar } => Some((
+            scalar,
+            match size {
+                VectorSize::Bi => 2,
+                VectorSize::Tri => 3,
+                VectorSize::Quad => 4,
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Vertex format matching a shader input type
+fn vertex_format(inner: &TypeInner) -> Result<wgpu::VertexFormat, String> {
+    use wgpu::VertexFormat as F;
+    let (scalar, components) =
+        vector_parts(inner).ok_or_else(|| format!("Unsupported vertex input type {:?}", inner))?;
+    let format = match (scalar.kind, scalar.width, components) {
+        (ScalarKind::Float, 4, 1) => F::Float32,
+        (ScalarKind::Float, 4, 2) => F::Float32x2,
+        (ScalarKind::Float, 4, 3) => F::Float32x3,
+        (ScalarKind::Float, 4, 4) => F::Float32x4,
+        (ScalarKind::Uint, 4, 1) => F::Uint32,
+        (ScalarKind::Uint, 4, 2) => F::Uint32x2,
+        (ScalarKind::Uint, 4, 3) => F::Uint32x3,
+        (ScalarKind::Uint, 4, 4) => F::Uint32x4,
+        (ScalarKind::Sint, 4, 1) => F::Sint32,
+        (ScalarKind::Sint, 4, 2) => F::Sint32x2,
+        (ScalarKind::Sint, 4, 3) => F::Sint32x3,
+        (ScalarKind::Sint, 4, 4) => F::Sint32x4,
+        _ => return Err(format!("Unsupported vertex input type {:?}", inner)),
+    };
+    Ok(format)
+}
+
+/// Color target format matching a fragment output type
+///
+/// Targets may have fewer components than the output, never more.
+fn target_format(inner: &TypeInner) -> Result<wgpu::TextureFormat, String> {
+    use wgpu::TextureFormat as F;
+    let (scalar, components) = vector_parts(inner)
+        .ok_or_else(|| format!("Unsupported fragment output type {:?}", inner))?;
+    let format = match (scalar.kind, components) {
+        (ScalarKind::Float, 1) => F::R16Float,
+        (ScalarKind::Float, 2 | 3) => F::Rg16Float,
+        (ScalarKind::Float, _) => F::Rgba16Float,
+        (ScalarKind::Uint, 1) => F::R32Uint,
+        (ScalarKind::Uint, 2 | 3) => F::Rg32Uint,
+        (ScalarKind::Uint, _) => F::Rgba32Uint,
+        (ScalarKind::Sint, 1) => F::R32Sint,
+        (ScalarKind::Sint, 2 | 3) => F::Rg32Sint,
+        (ScalarKind::Sint, _) => F::Rgba32Sint,
+        _ => return Err(format!("Unsupported fragment output type {:?}", inner)),
+    };
+    Ok(format)
+}
+
+/// Vertex attributes and color targets needed by a render pipeline
+struct RenderInterface {
+    attributes: Vec<wgpu::VertexAttribute>,
+    stride: u64,
+    targets: Vec<Option<wgpu::ColorTargetState>>,
+}
+
+fn render_interface(
+    module: &Module,
+    vertex: &str,
+    fragment: Option<&str>,
+) -> Result<RenderInterface, String> {
+    let entry = |name: &str| {
+        module
+            .entry_points
+            .iter()
+            .find(|ep| ep.name == name)
+            .ok_or_else(|| format!("Entry point '{}' not found", name))
+    };
+
+    let vertex_ep = entry(vertex)?;
+    let inputs = located_types(
+        module,
+        vertex_ep
+            .function
+            .arguments
+            .iter()
+            .map(|arg| (arg.binding.clone(), arg.ty)),
+    );
+    let mut attributes = Vec::with_capacity(inputs.len());
+    let mut stride = 0;
+    for (location, inner) in &inputs {
+        let format = vertex_format(inner)?;
+        attributes.push(wgpu::VertexAttribute {
+            format,
+            offset: stride,
+            shader_location: *location,
+        });
+        stride += format.size();
+    }
+
+    let mut targets = Vec::new();
+    if let Some(fragment) = fragment {
+        let result = entry(fragment)?.function.result.as_ref();
+        let outputs = located_types(
+            module,
+            result.into_iter().map(|r| (r.binding.clone(), r.ty)),
+        );
+        for (location, inner) in &outputs {
+            let location = *location as usize;
+            if targets.len() <= location {
+                targets.resize(location + 1, None);
+            }
+            targets[location] = Some(wgpu::ColorTargetState {
+                format: target_format(inner)?,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            });
+        }
+    }
+
+    Ok(RenderInterface {
+        attributes,
+        stride,
+        targets,
+    })
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    target: &PipelineTarget,
+    interface: Option<&RenderInterface>,
+) {
+    match (target, interface) {
+        (PipelineTarget::Compute { entry_point }, _) => {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Benchmark Compute Pipeline"),
+                layout: None,
+                module: shader,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+        }
+        (PipelineTarget::Render { vertex, fragment }, Some(interface)) => {
+            let buffers = if interface.attributes.is_empty() {
+                Vec::new()
+            } else {
+                vec![wgpu::VertexBufferLayout {
+                    array_stride: interface.stride,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &interface.attributes,
+                }]
+            };
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Benchmark Render Pipeline"),
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: shader,
+                    entry_point: Some(vertex),
+                    buffers: &buffers,
+                    compilation_options: Default::default(),
+                },
+                fragment: fragment.as_deref().map(|fragment| wgpu::FragmentState {
+                    module: shader,
+                    entry_point: Some(fragment),
+                    targets: &interface.targets,
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            });
+        }
+        (PipelineTarget::Render { .. }, None) => {}
+    }
+}
+
+/// Compile the shader `config.iterations` times on the device
+///
+/// Each iteration runs naga's front end, creates a shader module and, if
+/// enabled and the shader has usable entry points, a pipeline. Validation
+/// errors from the device abort the benchmark after the first iteration.
+pub fn benchmark_compile(
+    device: &wgpu::Device,
+    source: &str,
+    config: &BenchmarkConfig,
+) -> Result<BenchmarkReport, String> {
+    let iterations = config.iterations.clamp(1, MAX_ITERATIONS);
+    let module = front_end(source)?;
+    let target = if config.create_pipeline {
+        pipeline_target(&module)
+    } else {
+        None
+    };
+    let interface = match &target {
+        Some(PipelineTarget::Render { vertex, fragment }) => {
+            Some(render_interface(&module, vertex, fragment.as_deref())?)
+        }
+        _ => None,
+    };
+
+    let mut front_end_samples = Vec::with_capacity(iterations);
+    let mut module_samples = Vec::with_capacity(iterations);
+    let mut pipeline_samples = Vec::with_capacity(iterations);
+
+    for i in 0..iterations {
+        let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let start = Instant::now();
+        front_end(source)?;
+        front_end_samples.push(start.elapsed());
+
+        let start = Instant::now();
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Benchmark Shader"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        module_samples.push(start.elapsed());
+
+        if let Some(target) = &target {
+            let start = Instant::now();
+            create_pipeline(device, &shader, target, interface.as_ref());
+            pipeline_samples.push(start.elapsed());
+        }
+
+        // Only the first iteration is checked; later scopes are popped on drop.
+        // Web devices report errors asynchronously, so they are not waited for.
+        #[cfg(not(target_arch = "wasm32"))]
+        if i == 0 {
+            if let Some(error) = pollster::block_on(scope.pop()) {
+                return Err(format!("Device rejected the shader: {}", error));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = (i, scope);
+    }
+
+    Ok(BenchmarkReport {
+        iterations,
+        front_end: PhaseTiming::from_samples(&front_end_samples).expect("at least one sample"),
+        shader_module: PhaseTiming::from_samples(&module_samples).expect("at least one sample"),
+        pipeline: PhaseTiming::from_samples(&pipeline_samples),
+        pipeline_target: target,
+    })
+}
+
+/// Format a duration in milliseconds for display
+pub fn format_ms(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_stats() {
+        assert!(TimingStats::from_samples(&[]).is_none());
+
+        let samples: Vec<_> = [4, 1, 3, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let stats = TimingStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(4));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        // Population standard deviation of 1, 2, 3, 4 is sqrt(1.25)
+        assert!((stats.std_dev.as_secs_f64() * 1000.0 - 1.25f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_front_end_benchmark() {
+        let timing = benchmark_front_end("@compute @workgroup_size(1) fn main() {}", 5).unwrap();
+        assert_eq!(timing.warm.unwrap().count, 4);
+
+        let single = benchmark_front_end("@compute @workgroup_size(1) fn main() {}", 0).unwrap();
+        assert!(single.warm.is_none());
+
+        assert!(benchmark_front_end("fn broken(", 3).is_err());
+    }
+
+    #[test]
+    fn test_render_interface_from_entry_points() {
+        let source = r#"
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) id: u32,
+}
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+    @location(2) object: u32,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(in.position, 1.0);
+}
+
+@fragment
+fn fs_main() -> FragmentOutput {
+    return FragmentOutput(vec4<f32>(1.0), 7u);
+}
+"#;
+        let module = front_end(source).unwrap();
+        let target = pipeline_target(&module).unwrap();
+        assert_eq!(
+            target,
+            PipelineTarget::Render {
+                vertex: "vs_main".to_string(),
+                fragment: Some("fs_main".to_string()),
+            }
+        );
+        assert_eq!(target.to_string(), "render pipeline (vs_main + fs_main)");
+
+        let interface = render_interface(&module, "vs_main", Some("fs_main")).unwrap();
+        let formats: Vec<_> = interface.attributes.iter().map(|a| a.format).collect();
+        assert_eq!(
+            formats,
+            vec![
+                wgpu::VertexFormat::Float32x3,
+                wgpu::VertexFormat::Float32x2,
+                wgpu::VertexFormat::Uint32
+            ]
+        );
+        assert_eq!(interface.attributes[2].offset, 20);
+        assert_eq!(interface.stride, 24);
+
+        assert_eq!(interface.targets.len(), 3);
+        assert_eq!(
+            interface.targets[0].as_ref().unwrap().format,
+            wgpu::TextureFormat::Rgba16Float
+        );
+        assert!(interface.targets[1].is_none());
+        assert_eq!(
+            interface.targets[2].as_ref().unwrap().format,
+            wgpu::TextureFormat::R32Uint
+        );
+    }
+
+    #[test]
+    fn test_compute_target() {
+        let module = front_end("@compute @workgroup_size(8) fn cs() {}").unwrap();
+        assert_eq!(
+            pipeline_target(&module),
+            Some(PipelineTarget::Compute {
+                entry_point: "cs".to_string()
+            })
+        );
+    }
+}
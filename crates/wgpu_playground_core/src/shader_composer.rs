@@ -0,0 +1,1051 @@
+//! Module-style WGSL composition
+//!
+//! Lets shaders share code through importable modules, in the style of
+//! naga_oil. A module names itself with a `#define_import_path` directive
+//! and other sources pull it in with `#import`:
+//!
+//! ```wgsl
+//! #define_import_path playground::lighting
+//! #import playground::math
+//!
+//! fn lambert(normal: vec3<f32>, light_dir: vec3<f32>) -> f32 {
+//!     return math::saturate(dot(normal, light_dir));
+//! }
+//! ```
+//!
+//! Three import forms are supported:
+//!
+//! - `#import playground::math` makes items available as `math::item`
+//!   (and as `playground::math::item`)
+//! - `#import playground::math as m` makes them available as `m::item`
+//! - `#import playground::math::{PI, saturate}` makes the listed items
+//!   available by their bare names
+//!
+//! Composition places every imported module once, dependencies first,
+//! ahead of the importing source. Module items are renamed to
+//! `playground__math__saturate` and so on, so two modules may declare
+//! items with the same name. Directive lines are blanked rather than
+//! removed, which keeps line numbers of each part intact
+//! ([`ComposedShader::locate`] maps them back).
+//!
+//! Renaming is lexical: member names after `.`, attribute names after `@`
+//! and names followed by a single `:` (struct fields, parameters, typed
+//! `let`s) are left alone.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+
+/// File name prefix of shader library modules in the shaders directory
+pub const LIBRARY_PREFIX: &str = "lib_";
+
+/// Errors that can occur while composing a shader
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposeError {
+    /// A `#` directive could not be parsed
+    MalformedDirective {
+        module: Option<String>,
+        line: usize,
+        message: String,
+    },
+    /// A module source lacks `#define_import_path`
+    MissingImportPath,
+    /// An import names a module that is not registered
+    UnknownModule {
+        module: Option<String>,
+        line: usize,
+        path: String,
+    },
+    /// An import lists an item the module does not declare
+    UnknownItem {
+        module: Option<String>,
+        line: usize,
+        import: String,
+        item: String,
+    },
+    /// A `namespace::item` reference matches no imported item
+    UnresolvedPath {
+        module: Option<String>,
+        line: usize,
+        path: String,
+    },
+    /// Modules import each other; lists the cycle, first module repeated
+    ImportCycle(Vec<String>),
+}
+
+/// Where an error happened, for messages
+struct Location<'a>(&'a Option<String>, usize);
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(module) => write!(f, "{}, line {}", module, self.1),
+            None => write!(f, "line {}", self.1),
+        }
+    }
+}
+
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComposeError::MalformedDirective {
+                module,
+                line,
+                message,
+            } => write!(f, "{}: {}", Location(module, *line), message),
+            ComposeError::MissingImportPath => {
+                write!(f, "Module has no #define_import_path directive")
+            }
+            ComposeError::UnknownModule { module, line, path } => {
+                write!(f, "{}: unknown module '{}'", Location(module, *line), path)
+            }
+            ComposeError::UnknownItem {
+                module,
+                line,
+                import,
+                item,
+            } => write!(
+                f,
+                "{}: module '{}' has no item '{}'",
+                Location(module, *line),
+                import,
+                item
+            ),
+            ComposeError::UnresolvedPath { module, line, path } => write!(
+                f,
+                "{}: '{}' does not name an item of an imported module",
+                Location(module, *line),
+                path
+            ),
+            ComposeError::ImportCycle(cycle) => {
+                write!(f, "Import cycle: {}", cycle.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComposeError {}
+
+impl ComposeError {
+    /// Module (`None` for the composed shader itself) and line of the error
+    pub fn location(&self) -> Option<(Option<&str>, usize)> {
+        match self {
+            ComposeError::MalformedDirective { module, line, .. }
+            | ComposeError::UnknownModule { module, line, .. }
+            | ComposeError::UnknownItem { module, line, .. }
+            | ComposeError::UnresolvedPath { module, line, .. } => Some((module.as_deref(), *line)),
+            ComposeError::MissingImportPath | ComposeError::ImportCycle(_) => None,
+        }
+    }
+}
+
+/// One `#import` directive
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    /// Imported module, or for `a::b::item` possibly an item of `a::b`
+    pub path: String,
+    /// Namespace given with `as`
+    pub alias: Option<String>,
+    /// Items listed with `::{a, b}`
+    pub items: Vec<String>,
+    /// Line of the directive (1-indexed)
+    pub line: usize,
+}
+
+/// A source with its directives parsed and blanked out
+#[derive(Debug, Clone)]
+struct ParsedSource {
+    import_path: Option<String>,
+    imports: Vec<Import>,
+    body: String,
+}
+
+/// A registered module
+#[derive(Debug, Clone)]
+pub struct ComposableModule {
+    /// Path from `#define_import_path`
+    pub path: String,
+    /// Top-level items the module declares, in source order
+    pub items: Vec<String>,
+    /// Imports of the module itself
+    pub imports: Vec<Import>,
+    body: String,
+}
+
+/// Result of composing a shader
+#[derive(Debug, Clone)]
+pub struct ComposedShader {
+    /// Plain WGSL that naga and wgpu accept
+    pub source: String,
+    /// Imported modules in the order they were placed
+    pub modules: Vec<String>,
+    /// Part of the source each line range came from
+    sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone)]
+struct Section {
+    /// `None` for the composed shader itself
+    module: Option<String>,
+    /// First composed line of the part (1-indexed)
+    first_line: usize,
+    line_count: usize,
+}
+
+impl ComposedShader {
+    /// Map a line of the composed source back to its origin
+    ///
+    /// Returns the module (`None` for the composed shader itself) and the
+    /// line within it, both 1-indexed like the input.
+    pub fn locate(&self, line: usize) -> Option<(Option<&str>, usize)> {
+        self.sections
+            .iter()
+            .find(|section| {
+                line >= section.first_line && line < section.first_line + section.line_count
+            })
+            .map(|section| (section.module.as_deref(), line - section.first_line + 1))
+    }
+}
+
+/// Import relationships of a shader, for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportGraph {
+    /// The shader itself first, then its modules dependencies first
+    pub nodes: Vec<ImportNode>,
+    /// `(importer, imported)` node indices
+    pub edges: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportNode {
+    pub name: String,
+    /// Length of the longest import chain from the shader
+    pub depth: usize,
+    /// Items the module declares; empty for the shader itself
+    pub items: Vec<String>,
+}
+
+/// Registry of importable modules
+#[derive(Debug, Clone, Default)]
+pub struct ShaderComposer {
+    modules: BTreeMap<String, ComposableModule>,
+}
+
+impl ShaderComposer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Composer with the shader library modules registered
+    ///
+    /// Loads every `lib_*.wgsl` file from the bundled shaders directory,
+    /// then from the user assets directory, so user modules can replace
+    /// bundled ones with the same import path. Files that fail to parse
+    /// are skipped with a warning.
+    pub fn with_library() -> Self {
+        let mut composer = Self::new();
+        for path in library_files() {
+            let source = match crate::assets::load_string_from_path(&path) {
+                Ok(source) => source,
+                Err(e) => {
+                    log::warn!("Failed to read shader module {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            match composer.add_module(&source) {
+                Ok(import_path) => {
+                    log::debug!(
+                        "Loaded shader module {} from {}",
+                        import_path,
+                        path.display()
+                    )
+                }
+                Err(e) => log::warn!("Skipping shader module {}: {}", path.display(), e),
+            }
+        }
+        composer
+    }
+
+    /// Register a module, replacing one with the same import path
+    ///
+    /// Returns the module's import path.
+    pub fn add_module(&mut self, source: &str) -> Result<String, ComposeError> {
+        let parsed = parse_source(source)?;
+        let path = parsed.import_path.ok_or(ComposeError::MissingImportPath)?;
+        let tokens = tokenize(&parsed.body);
+        let items = declarations(&tokens)
+            .into_iter()
+            .map(|index| tokens[index].text.to_string())
+            .collect();
+        self.modules.insert(
+            path.clone(),
+            ComposableModule {
+                path: path.clone(),
+                items,
+                imports: parsed.imports,
+                body: parsed.body,
+            },
+        );
+        Ok(path)
+    }
+
+    /// Look up a registered module
+    pub fn module(&self, path: &str) -> Option<&ComposableModule> {
+        self.modules.get(path)
+    }
+
+    /// Registered modules, ordered by import path
+    pub fn modules(&self) -> impl Iterator<Item = &ComposableModule> {
+        self.modules.values()
+    }
+
+    /// Resolve the imports of `source` into plain WGSL
+    pub fn compose(&self, source: &str) -> Result<ComposedShader, ComposeError> {
+        let root = parse_source(source)?;
+        let order = self.import_order(&root)?;
+
+        let mut composed = String::new();
+        let mut sections = Vec::new();
+        let mut line = 1;
+        for path in &order {
+            let module = &self.modules[path];
+            let body = self.rewrite(&module.body, Some(module), &module.imports)?;
+            composed.push_str(&format!("// module {}\n", path));
+            line += 1;
+            let line_count = push_lines(&mut composed, &body);
+            sections.push(Section {
+                module: Some(path.clone()),
+                first_line: line,
+                line_count,
+            });
+            line += line_count;
+        }
+
+        let body = self.rewrite(&root.body, None, &root.imports)?;
+        let line_count = push_lines(&mut composed, &body);
+        sections.push(Section {
+            module: None,
+            first_line: line,
+            line_count,
+        });
+
+        Ok(ComposedShader {
+            source: composed,
+            modules: order,
+            sections,
+        })
+    }
+
+    /// Modules `source` imports, directly or not, and how they connect
+    pub fn import_graph(&self, source: &str) -> Result<ImportGraph, ComposeError> {
+        let root = parse_source(source)?;
+        let order = self.import_order(&root)?;
+
+        let mut nodes = vec![ImportNode {
+            name: root
+                .import_path
+                .clone()
+                .unwrap_or_else(|| "shader".to_string()),
+            depth: 0,
+            items: Vec::new(),
+        }];
+        let index: HashMap<&str, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, path)| (path.as_str(), i + 1))
+            .collect();
+        nodes.extend(order.iter().map(|path| ImportNode {
+            name: path.clone(),
+            depth: 0,
+            items: self.modules[path].items.clone(),
+        }));
+
+        let mut edges = Vec::new();
+        let imports_of = |node: usize| match node {
+            0 => &root.imports,
+            _ => &self.modules[&order[node - 1]].imports,
+        };
+        for node in 0..nodes.len() {
+            for import in imports_of(node) {
+                let (module, _) = self.resolve(import, None)?;
+                let edge = (node, index[module.path.as_str()]);
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        }
+
+        // Dependencies come after their importers when walking the order
+        // backwards, so one pass finds the longest chains
+        let mut walk: Vec<usize> = (1..nodes.len()).rev().collect();
+        walk.insert(0, 0);
+        for node in walk {
+            let depth = nodes[node].depth;
+            for &(from, to) in &edges {
+                if from == node && nodes[to].depth < depth + 1 {
+                    nodes[to].depth = depth + 1;
+                }
+            }
+        }
+
+        Ok(ImportGraph { nodes, edges })
+    }
+
+    /// Imported modules, dependencies before the modules importing them
+    fn import_order(&self, root: &ParsedSource) -> Result<Vec<String>, ComposeError> {
+        let mut stack: Vec<String> = root.import_path.iter().cloned().collect();
+        let mut order = Vec::new();
+        for import in &root.imports {
+            self.visit(import, None, &mut stack, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    fn visit(
+        &self,
+        import: &Import,
+        importer: Option<&str>,
+        stack: &mut Vec<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), ComposeError> {
+        let (module, _) = self.resolve(import, importer)?;
+        if order.contains(&module.path) {
+            return Ok(());
+        }
+        if let Some(start) = stack.iter().position(|path| *path == module.path) {
+            let mut cycle = stack[start..].to_vec();
+            cycle.push(module.path.clone());
+            return Err(ComposeError::ImportCycle(cycle));
+        }
+
+        stack.push(module.path.clone());
+        for dependency in &module.imports {
+            self.visit(dependency, Some(module.path.as_str()), stack, order)?;
+        }
+        stack.pop();
+        order.push(module.path.clone());
+        Ok(())
+    }
+
+    /// Find the module of an import and the items it lists
+    ///
+    /// `#import a::b::item` imports a single item when `a::b::item` is not
+    /// a module itself.
+    fn resolve(
+        &self,
+        import: &Import,
+        importer: Option<&str>,
+    ) -> Result<(&ComposableModule, Vec<String>), ComposeError> {
+        let unknown_module = || ComposeError::UnknownModule {
+            module: importer.map(str::to_string),
+            line: import.line,
+            path: import.path.clone(),
+        };
+
+        let (module, items) = match self.modules.get(&import.path) {
+            Some(module) => (module, import.items.clone()),
+            None if import.items.is_empty() && import.alias.is_none() => {
+                let (parent, item) = import.path.rsplit_once("::").ok_or_else(unknown_module)?;
+                let module = self.modules.get(parent).ok_or_else(unknown_module)?;
+                (module, vec![item.to_string()])
+            }
+            None => return Err(unknown_module()),
+        };
+
+        for item in &items {
+            if !module.items.contains(item) {
+                return Err(ComposeError::UnknownItem {
+                    module: importer.map(str::to_string),
+                    line: import.line,
+                    import: module.path.clone(),
+                    item: item.clone(),
+                });
+            }
+        }
+        Ok((module, items))
+    }
+
+    /// Rename module items in a source body
+    ///
+    /// `own` is the module the body belongs to, whose items get their
+    /// mangled names too.
+    fn rewrite(
+        &self,
+        body: &str,
+        own: Option<&ComposableModule>,
+        imports: &[Import],
+    ) -> Result<String, ComposeError> {
+        let importer = own.map(|module| module.path.as_str());
+        let mut names: HashMap<String, String> = HashMap::new();
+        if let Some(module) = own {
+            for item in &module.items {
+                names.insert(item.clone(), mangle(&module.path, item));
+            }
+        }
+        for import in imports {
+            let (module, items) = self.resolve(import, importer)?;
+            if items.is_empty() {
+                let namespace = import
+                    .alias
+                    .as_deref()
+                    .unwrap_or_else(|| last_segment(&module.path));
+                for item in &module.items {
+                    let mangled = mangle(&module.path, item);
+                    names.insert(format!("{}::{}", namespace, item), mangled.clone());
+                    names.insert(format!("{}::{}", module.path, item), mangled);
+                }
+            } else {
+                for item in items {
+                    let mangled = mangle(&module.path, &item);
+                    names.insert(item, mangled);
+                }
+            }
+        }
+
+        let tokens = tokenize(body);
+        let declared: HashSet<usize> = declarations(&tokens).into_iter().collect();
+        let mut rewritten = String::with_capacity(body.len());
+        let mut copied = 0;
+        for (index, token) in tokens.iter().enumerate() {
+            if token.kind != TokenKind::Name {
+                continue;
+            }
+            let previous = index.checked_sub(1).map(|i| tokens[i].kind);
+            if matches!(previous, Some(TokenKind::Punct('.' | '@'))) {
+                continue;
+            }
+            let replacement = if token.text.contains("::") {
+                match names.get(token.text) {
+                    Some(mangled) => mangled,
+                    None => {
+                        return Err(ComposeError::UnresolvedPath {
+                            module: importer.map(str::to_string),
+                            line: line_of(body, token.start),
+                            path: token.text.to_string(),
+                        })
+                    }
+                }
+            } else {
+                let next = tokens.get(index + 1).map(|next| next.kind);
+                if !declared.contains(&index) && next == Some(TokenKind::Punct(':')) {
+                    continue;
+                }
+                match names.get(token.text) {
+                    Some(mangled) => mangled,
+                    None => continue,
+                }
+            };
+            rewritten.push_str(&body[copied..token.start]);
+            rewritten.push_str(replacement);
+            copied = token.start + token.text.len();
+        }
+        rewritten.push_str(&body[copied..]);
+        Ok(rewritten)
+    }
+}
+
+/// Whether a source uses `#import`
+pub fn has_imports(source: &str) -> bool {
+    source
+        .lines()
+        .any(|line| line.trim_start().starts_with("#import"))
+}
+
+/// Library module files, bundled ones first
+#[cfg(not(target_arch = "wasm32"))]
+pub fn library_files() -> Vec<PathBuf> {
+    let mut dirs = vec![crate::assets::builtin_assets_dir()];
+    dirs.extend(crate::assets::user_assets_dir());
+
+    let mut files = Vec::new();
+    for dir in dirs {
+        let dir = dir.join(crate::assets::AssetKind::Shader.subdir());
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == "wgsl")
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(LIBRARY_PREFIX))
+            })
+            .collect();
+        found.sort();
+        files.extend(found);
+    }
+    files
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn library_files() -> Vec<PathBuf> {
+    // Directories cannot be listed on the web
+    Vec::new()
+}
+
+/// Name a module item gets in composed source
+pub fn mangle(module: &str, item: &str) -> String {
+    format!("{}__{}", module.replace("::", "__"), item)
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_path(text: &str) -> bool {
+    text.split("::").all(is_identifier)
+}
+
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+/// Append `text` as whole lines, returning how many were added
+fn push_lines(out: &mut String, text: &str) -> usize {
+    out.push_str(text);
+    if !text.ends_with('\n') {
+        out.push('\n');
+    }
+    text.lines().count().max(1)
+}
+
+/// Parse the directives of a source and blank their lines
+fn parse_source(source: &str) -> Result<ParsedSource, ComposeError> {
+    let mut import_path = None;
+    let mut imports = Vec::new();
+    let mut body = Vec::new();
+
+    for (index, text) in source.split('\n').enumerate() {
+        let line = index + 1;
+        let trimmed = text.trim();
+        let malformed = |message: String| ComposeError::MalformedDirective {
+            module: import_path.clone(),
+            line,
+            message,
+        };
+
+        if let Some(rest) = trimmed.strip_prefix("#define_import_path") {
+            let path = rest.trim();
+            if !is_path(path) {
+                return Err(malformed(format!("invalid import path '{}'", path)));
+            }
+            import_path = Some(path.to_string());
+            body.push("");
+        } else if let Some(rest) = trimmed.strip_prefix("#import") {
+            imports.push(parse_import(rest, line).map_err(malformed)?);
+            body.push("");
+        } else if trimmed.starts_with('#') {
+            return Err(malformed(format!("unsupported directive '{}'", trimmed)));
+        } else {
+            body.push(text);
+        }
+    }
+
+    Ok(ParsedSource {
+        import_path,
+        imports,
+        body: body.join("\n"),
+    })
+}
+
+fn parse_import(text: &str, line: usize) -> Result<Import, String> {
+    let text = text.trim().trim_end_matches(';').trim();
+    let mut import = Import {
+        path: text.to_string(),
+        alias: None,
+        items: Vec::new(),
+        line,
+    };
+
+    if let Some((path, items)) = text.split_once("::{") {
+        let items = items
+            .strip_suffix('}')
+            .ok_or_else(|| format!("missing '}}' in import '{}'", text))?;
+        import.path = path.trim().to_string();
+        import.items = items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some(item) = import.items.iter().find(|item| !is_identifier(item)) {
+            return Err(format!("invalid item name '{}'", item));
+        }
+    } else if let Some((path, alias)) = text.split_once(" as ") {
+        let alias = alias.trim();
+        if !is_identifier(alias) {
+            return Err(format!("invalid alias '{}'", alias));
+        }
+        import.path = path.trim().to_string();
+        import.alias = Some(alias.to_string());
+    }
+
+    if !is_path(&import.path) {
+        return Err(format!("invalid import path '{}'", import.path));
+    }
+    Ok(import)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    /// Identifier or `a::b` path, including keywords
+    Name,
+    Number,
+    Punct(char),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    /// Byte offset in the source
+    start: usize,
+}
+
+/// Split WGSL into tokens, skipping whitespace and comments
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let bytes = source.as_bytes();
+    let is_name_start = |b: u8| b.is_ascii_alphabetic() || b == b'_';
+    let is_name_char = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let start = i;
+        if b.is_ascii_whitespace() {
+            i += 1;
+        } else if bytes[i..].starts_with(b"//") {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if bytes[i..].starts_with(b"/*") {
+            // Block comments nest in WGSL
+            let mut depth = 0;
+            while i < bytes.len() {
+                if bytes[i..].starts_with(b"/*") {
+                    depth += 1;
+                    i += 2;
+                } else if bytes[i..].starts_with(b"*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+        } else if is_name_start(b) {
+            loop {
+                while i < bytes.len() && is_name_char(bytes[i]) {
+                    i += 1;
+                }
+                if bytes[i..].starts_with(b"::")
+                    && bytes.get(i + 2).is_some_and(|&b| is_name_start(b))
+                {
+                    i += 2;
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Name,
+                text: &source[start..i],
+                start,
+            });
+        } else if b.is_ascii_digit() {
+            let hex = bytes[i..].starts_with(b"0x") || bytes[i..].starts_with(b"0X");
+            i += 1;
+            while i < bytes.len() {
+                let c = bytes[i];
+                let exponent_sign = (c == b'+' || c == b'-')
+                    && match bytes[i - 1] {
+                        b'p' | b'P' => true,
+                        b'e' | b'E' => !hex,
+                        _ => false,
+                    };
+                if is_name_char(c) || c == b'.' || exponent_sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Number,
+                text: &source[start..i],
+                start,
+            });
+        } else {
+            let c = source[i..].chars().next().unwrap_or('\u{fffd}');
+            i += c.len_utf8();
+            tokens.push(Token {
+                kind: TokenKind::Punct(c),
+                text: &source[start..i],
+                start,
+            });
+        }
+    }
+    tokens
+}
+
+/// Indices of the name tokens of top-level declarations
+fn declarations(tokens: &[Token]) -> Vec<usize> {
+    let mut names = Vec::new();
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::Punct('{') => depth += 1,
+            TokenKind::Punct('}') => depth = depth.saturating_sub(1),
+            TokenKind::Name if depth == 0 => {
+                let mut name = index + 1;
+                match token.text {
+                    "fn" | "struct" | "alias" | "const" | "override" => {}
+                    "var" => {
+                        // Skip an address space like `var<uniform>`
+                        if tokens.get(name).map(|t| t.kind) == Some(TokenKind::Punct('<')) {
+                            while name < tokens.len() && tokens[name].kind != TokenKind::Punct('>')
+                            {
+                                name += 1;
+                            }
+                            name += 1;
+                        }
+                    }
+                    _ => continue,
+                }
+                if tokens.get(name).map(|t| t.kind) == Some(TokenKind::Name) {
+                    names.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MATH: &str = "#define_import_path playground::math
+
+const PI: f32 = 3.14159;
+
+fn saturate(x: f32) -> f32 {
+    return clamp(x, 0.0, 1.0);
+}
+";
+
+    const LIGHTING: &str = "#define_import_path playground::lighting
+#import playground::math
+
+fn lambert(normal: vec3<f32>, light_dir: vec3<f32>) -> f32 {
+    return math::saturate(dot(normal, light_dir));
+}
+";
+
+    fn composer() -> ShaderComposer {
+        let mut composer = ShaderComposer::new();
+        composer.add_module(MATH).unwrap();
+        composer.add_module(LIGHTING).unwrap();
+        composer
+    }
+
+    fn assert_valid_wgsl(source: &str) {
+        let module = naga::front::wgsl::parse_str(source)
+            .unwrap_or_else(|e| panic!("{}\n{}", e.emit_to_string(source), source));
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::default(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_tokenize_skips_comments() {
+        let tokens = tokenize("a /* b /* c */ d */ e // f\n1.5e-3 x::y.z");
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text).collect();
+        assert_eq!(texts, ["a", "e", "1.5e-3", "x::y", ".", "z"]);
+    }
+
+    #[test]
+    fn test_declarations() {
+        let source = "struct S { a: f32 }\nvar<uniform> u: S;\nconst C = 1;\n\
+                      fn f() { const local = 2; }";
+        let tokens = tokenize(source);
+        let names: Vec<&str> = declarations(&tokens)
+            .into_iter()
+            .map(|i| tokens[i].text)
+            .collect();
+        assert_eq!(names, ["S", "u", "C", "f"]);
+    }
+
+    #[test]
+    fn test_parse_imports() {
+        let parsed =
+            parse_source("#import a::b\n#import a::b as c\n#import a::b::{x, y}\nfn main() {}")
+                .unwrap();
+        assert_eq!(parsed.imports[0].path, "a::b");
+        assert_eq!(parsed.imports[1].alias.as_deref(), Some("c"));
+        assert_eq!(parsed.imports[2].items, ["x", "y"]);
+        assert_eq!(parsed.imports[2].line, 3);
+        // Directive lines are blanked so line numbers stay put
+        assert_eq!(parsed.body, "\n\n\nfn main() {}");
+
+        assert!(parse_source("#import a::{x").is_err());
+        assert!(parse_source("#import 3d").is_err());
+        assert!(parse_source("#ifdef FOO").is_err());
+    }
+
+    #[test]
+    fn test_compose_namespaced_imports() {
+        let source = "#import playground::lighting
+#import playground::math as m
+
+@fragment
+fn fs_main(@location(0) normal: vec3<f32>) -> @location(0) vec4<f32> {
+    let light = lighting::lambert(normal, vec3<f32>(0.0, 1.0, 0.0));
+    return vec4<f32>(vec3<f32>(light * m::PI), 1.0);
+}
+";
+        let composed = composer().compose(source).unwrap();
+        assert_eq!(
+            composed.modules,
+            ["playground::math", "playground::lighting"]
+        );
+        assert!(composed.source.contains("fn playground__math__saturate"));
+        assert!(composed
+            .source
+            .contains("playground__lighting__lambert(normal"));
+        assert_valid_wgsl(&composed.source);
+    }
+
+    #[test]
+    fn test_compose_item_imports() {
+        let source = "#import playground::math::{saturate, PI}
+#import playground::lighting::lambert
+
+fn shade(n: vec3<f32>) -> f32 {
+    return saturate(lambert(n, n) * PI);
+}
+";
+        let composed = composer().compose(source).unwrap();
+        assert!(composed
+            .source
+            .contains("playground__math__saturate(playground__lighting__lambert(n, n) * playground__math__PI)"));
+        assert_valid_wgsl(&composed.source);
+    }
+
+    #[test]
+    fn test_modules_may_share_item_names() {
+        let mut composer = composer();
+        composer
+            .add_module("#define_import_path other\nfn saturate(x: f32) -> f32 { return x; }")
+            .unwrap();
+        let source = "#import other\n#import playground::math\n\
+                      fn f() -> f32 { return other::saturate(math::saturate(2.0)); }";
+        assert_valid_wgsl(&composer.compose(source).unwrap().source);
+    }
+
+    #[test]
+    fn test_locate_lines() {
+        let source = "#import playground::math\n\nfn f() -> f32 { return math::PI; }";
+        let composed = composer().compose(source).unwrap();
+        let line = composed
+            .source
+            .lines()
+            .position(|l| l.contains("fn f()"))
+            .unwrap()
+            + 1;
+        assert_eq!(composed.locate(line), Some((None, 3)));
+
+        let line = composed
+            .source
+            .lines()
+            .position(|l| l.contains("fn playground__math__saturate"))
+            .unwrap()
+            + 1;
+        assert_eq!(composed.locate(line), Some((Some("playground::math"), 5)));
+    }
+
+    #[test]
+    fn test_compose_errors() {
+        let composer = composer();
+        assert!(matches!(
+            composer.compose("#import missing"),
+            Err(ComposeError::UnknownModule { line: 1, .. })
+        ));
+        assert!(matches!(
+            composer.compose("#import playground::math::{nope}"),
+            Err(ComposeError::UnknownItem { .. })
+        ));
+        assert!(matches!(
+            composer.compose("#import playground::math\nfn f() -> f32 { return math::E; }"),
+            Err(ComposeError::UnresolvedPath { line: 2, .. })
+        ));
+        assert_eq!(
+            ShaderComposer::new().add_module("fn f() {}"),
+            Err(ComposeError::MissingImportPath)
+        );
+    }
+
+    #[test]
+    fn test_import_cycle() {
+        let mut composer = ShaderComposer::new();
+        composer
+            .add_module("#define_import_path a\n#import b\nfn fa() {}")
+            .unwrap();
+        composer
+            .add_module("#define_import_path b\n#import a\nfn fb() {}")
+            .unwrap();
+        assert_eq!(
+            composer.compose("#import a"),
+            Err(ComposeError::ImportCycle(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "a".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_import_graph() {
+        let source = "#import playground::lighting\n#import playground::math";
+        let graph = composer().import_graph(source).unwrap();
+        let names: Vec<&str> = graph.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["shader", "playground::math", "playground::lighting"]
+        );
+        // math is imported directly and through lighting; the longer chain wins
+        assert_eq!(graph.nodes[1].depth, 2);
+        assert_eq!(graph.nodes[2].depth, 1);
+        assert_eq!(graph.edges, [(0, 2), (0, 1), (2, 1)]);
+        assert_eq!(graph.nodes[1].items, ["PI", "saturate"]);
+    }
+
+    #[test]
+    fn test_bundled_library_composes() {
+        let composer = ShaderComposer::with_library();
+        if composer.module("playground::lighting").is_none() {
+            // Assets are not available, e.g. on the web
+            return;
+        }
+        let source = "#import playground::lighting\n#import playground::math\n\
+                      fn f(n: vec3<f32>, l: vec3<f32>, v: vec3<f32>) -> vec3<f32> {\n\
+                      return lighting::phong(n, l, v, vec3<f32>(1.0), 1.0) * math::PI;\n}";
+        assert_valid_wgsl(&composer.compose(source).unwrap().source);
+    }
+}
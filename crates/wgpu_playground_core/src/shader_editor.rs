@@ -1,6 +1,27 @@
 /// WGSL Shader Editor with syntax highlighting, line numbers, and compilation support
+use crate::buffer_layout_check::{
+    check_layout, parse_cpu_struct, reflect_buffer_bindings, LayoutMismatch, ShaderBufferBinding,
+};
+use crate::capability_snapshot::VirtualDevice;
+use crate::controller_input::{self, ControllerInput, MappingTarget};
+use crate::operation_errors::OperationErrors;
+use crate::pixel_picker::PixelPicker;
 use crate::shader::ShaderModule;
+use crate::shader_benchmark::{
+    benchmark_compile, format_ms, BenchmarkConfig, BenchmarkReport, PhaseTiming, MAX_ITERATIONS,
+};
+use crate::shader_composer::{
+    has_imports, ComposeError, ComposedShader, ImportGraph, ShaderComposer, LIBRARY_PREFIX,
+};
+use crate::shader_format::{format_wgsl, FormatError, FormatOptions, MAX_INDENT_WIDTH};
+use crate::shader_history::{diff_lines, diff_stats, hunks, DiffKind, ShaderHistory};
+use crate::shader_include::{
+    has_includes, shader_name, ExpandedShader, IncludeError, IncludeResolver,
+};
+use crate::shader_lint::{LintDiagnostic, LintSeverity, TargetProfile};
+use crate::shader_preview::{ShaderPreview, PREVIEW_SIZE};
 use crate::shader_watcher::ShaderWatcher;
+use crate::uniform_tweaker::UniformTweaker;
 
 /// Represents a validation error with location information
 #[derive(Debug, Clone)]
@@ -45,8 +66,72 @@ pub struct ShaderEditor {
     validation_errors: Vec<ValidationError>,
     /// Whether real-time validation is enabled
     realtime_validation_enabled: bool,
+    /// Target environment for the compatibility linter
+    lint_profile: TargetProfile,
+    /// Device offered as a lint target, if one is known
+    lint_device: Option<VirtualDevice>,
+    /// Result of the last lint run
+    lint_results: Option<Result<Vec<LintDiagnostic>, String>>,
+    /// Settings for compile benchmarking
+    benchmark_config: BenchmarkConfig,
+    /// Result of the last compile benchmark
+    benchmark_result: Option<Result<BenchmarkReport, String>>,
+    /// Device errors from compiling the shader module
+    gpu_errors: OperationErrors,
+    /// Library modules the shader can `#import`
+    composer: ShaderComposer,
+    /// Finds the files the shader can `#include`
+    includes: IncludeResolver,
+    /// Layout the Format action gives the source
+    format_options: FormatOptions,
+    /// Snapshots of every shader edited, saved with the project
+    history: ShaderHistory,
+    /// Name for the next manual snapshot
+    snapshot_name: String,
+    /// Versions compared in the history diff viewer
+    history_diff: Option<(Version, Version)>,
+    /// CPU struct compared with a buffer binding of the shader
+    layout_cpu_struct: String,
+    /// Group and binding of the buffer to compare
+    layout_binding: Option<(u32, u32)>,
+    /// Result of the last layout check
+    layout_check: Option<Result<Vec<LayoutMismatch>, String>>,
+    /// Values of the shader's uniform buffers
+    uniforms: UniformTweaker,
+    /// Live preview, created on first use
+    preview: Option<ShaderPreview>,
+    /// Source the preview and the uniforms were last built from
+    preview_source: Option<String>,
+    /// Shift-hover readback of the preview texels
+    pixel_picker: PixelPicker,
+    /// Gamepad and MIDI mappings to the uniforms and the vertex count
+    controllers: ControllerInput,
 }
 
+/// A shader version in the history diff viewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Version {
+    Snapshot(u64),
+    /// The source currently in the editor
+    Current,
+}
+
+/// Button clicked in a row of the history list
+enum HistoryAction {
+    Diff(u64),
+    Restore(u64),
+    Remove(u64),
+}
+
+/// Unchanged lines shown around each change in the history diff viewer
+const DIFF_CONTEXT: usize = 3;
+
+/// Operation name of shader module creation in [`OperationErrors`]
+const COMPILE: &str = "compile";
+
+/// Mapping target setting the number of vertices the preview draws
+const VERTICES_TARGET: &str = "Vertices";
+
 impl Default for ShaderEditor {
     fn default() -> Self {
         Self::new()
@@ -77,9 +162,160 @@ impl ShaderEditor {
             hot_reload_enabled: true,
             validation_errors: Vec::new(),
             realtime_validation_enabled: true,
+            lint_profile: TargetProfile::WebGpuCore,
+            lint_device: None,
+            lint_results: None,
+            benchmark_config: BenchmarkConfig::default(),
+            benchmark_result: None,
+            gpu_errors: OperationErrors::new(),
+            composer: ShaderComposer::with_library(),
+            includes: IncludeResolver::with_shader_dirs(),
+            format_options: FormatOptions::default(),
+            history: ShaderHistory::new(),
+            snapshot_name: String::new(),
+            history_diff: None,
+            layout_cpu_struct:
+                "#[repr(C)]\nstruct Uniforms {\n    color: [f32; 3],\n    time: f32,\n}\n"
+                    .to_string(),
+            layout_binding: None,
+            layout_check: None,
+            uniforms: UniformTweaker::new(),
+            preview: None,
+            preview_source: None,
+            pixel_picker: PixelPicker::new(),
+            controllers: ControllerInput::new(),
         }
     }
 
+    /// Restart hot reload watching, e.g. after the assets directory changed
+    pub fn restart_watcher(&mut self) {
+        self.shader_watcher = match ShaderWatcher::new() {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                log::warn!("Failed to restart shader watcher: {}", e);
+                None
+            }
+        };
+        self.reload_modules();
+    }
+
+    /// Reload the library modules, e.g. after one of them changed
+    pub fn reload_modules(&mut self) {
+        self.composer = ShaderComposer::with_library();
+        self.includes = IncludeResolver::with_shader_dirs();
+        self.preview_source = None;
+        if self.realtime_validation_enabled {
+            self.realtime_validate();
+        }
+    }
+
+    /// Path of the loaded file in the shaders directory, if any
+    fn shader_path(&self) -> Option<std::path::PathBuf> {
+        if self.file_path.is_empty() {
+            return None;
+        }
+        crate::assets::resolve(crate::assets::AssetKind::Shader, &self.file_path).ok()
+    }
+
+    /// Paste in the files the shader includes
+    ///
+    /// Returns `None` for a shader without includes.
+    fn expand(&self) -> Result<Option<ExpandedShader>, IncludeError> {
+        if !has_includes(&self.source_code) {
+            return Ok(None);
+        }
+        self.includes
+            .expand(&self.source_code, self.shader_path().as_deref())
+            .map(Some)
+    }
+
+    /// Compose `source`, the shader with its includes pasted in, with its
+    /// imported modules
+    ///
+    /// Returns `None` for a shader without imports.
+    fn compose(&self, source: &str) -> Result<Option<ComposedShader>, ComposeError> {
+        if !has_imports(source) {
+            return Ok(None);
+        }
+        self.composer.compose(source).map(Some)
+    }
+
+    /// Plain WGSL for the shader, with includes and imports resolved
+    fn composed_source(&self) -> Result<String, String> {
+        let expanded = self.expand().map_err(|e| format!("Include error: {}", e))?;
+        let source = expanded
+            .as_ref()
+            .map_or(self.source_code.as_str(), |expanded| {
+                expanded.source.as_str()
+            });
+        match self.compose(source) {
+            Ok(Some(composed)) => Ok(composed.source),
+            Ok(None) => Ok(source.to_string()),
+            Err(e) => Err(format!("Import error: {}", e)),
+        }
+    }
+
+    /// Uniform and storage buffer bindings of the shader
+    pub fn buffer_bindings(&self) -> Result<Vec<ShaderBufferBinding>, String> {
+        let source = self.composed_source()?;
+        let module =
+            naga::front::wgsl::parse_str(&source).map_err(|e| e.emit_to_string(&source))?;
+        reflect_buffer_bindings(&module)
+    }
+
+    /// Reflect the uniforms and rebuild the preview if the source changed
+    /// since the last build
+    fn refresh_preview(&mut self, device: &wgpu::Device) {
+        if self.preview_source.as_ref() == Some(&self.source_code) {
+            return;
+        }
+        self.preview_source = Some(self.source_code.clone());
+        let source = self.composed_source();
+        // Keep the old widgets while the source doesn't parse
+        if let Some(Ok(module)) = source.as_deref().ok().map(naga::front::wgsl::parse_str) {
+            if let Err(e) = self.uniforms.reflect(&module) {
+                log::warn!("Failed to reflect uniforms: {}", e);
+            }
+        }
+        let preview = self
+            .preview
+            .get_or_insert_with(|| ShaderPreview::new(device));
+        match source {
+            Ok(source) => preview.build(device, &source, &self.uniforms),
+            // The import error, shown in the editor too
+            Err(e) => preview.set_error(e),
+        }
+    }
+
+    /// Compare the CPU struct with the buffer at `group`/`binding`
+    pub fn check_buffer_layout(
+        &mut self,
+        group: u32,
+        binding: u32,
+    ) -> &Result<Vec<LayoutMismatch>, String> {
+        self.layout_binding = Some((group, binding));
+        let result = parse_cpu_struct(&self.layout_cpu_struct)
+            .map_err(|e| format!("CPU struct {}", e))
+            .and_then(|cpu| {
+                let bindings = self.buffer_bindings()?;
+                let shader = bindings
+                    .iter()
+                    .find(|b| b.group == group && b.binding == binding)
+                    .ok_or_else(|| {
+                        format!("No buffer at @group({}) @binding({})", group, binding)
+                    })?;
+                shader.layout.as_ref().map_err(Clone::clone)?;
+                Ok(check_layout(&cpu, shader))
+            });
+        self.layout_check.insert(result)
+    }
+
+    /// Set the CPU struct compared by [`check_buffer_layout`](Self::check_buffer_layout)
+    pub fn set_layout_cpu_struct(&mut self, source: String) {
+        self.layout_cpu_struct = source;
+        self.layout_check = None;
+    }
+
     /// Get default example shader code
     fn default_shader_code() -> String {
         r#"// WGSL Shader Example
@@ -122,6 +358,16 @@ fn fs_main() -> @location(0) vec4<f32> {
         }
     }
 
+    /// Replace the source with a shader from outside the shaders directory,
+    /// such as a dropped file, labeled `label`
+    ///
+    /// The editor no longer refers to a file, so hot reload stops.
+    pub fn open_source(&mut self, label: &str, code: String) {
+        self.label = label.to_string();
+        self.file_path.clear();
+        self.set_source_code(code);
+    }
+
     /// Get the current source code
     pub fn source_code(&self) -> &str {
         &self.source_code
@@ -137,6 +383,85 @@ fn fs_main() -> @location(0) vec4<f32> {
         }
     }
 
+    /// Reformat the source with the current format options
+    ///
+    /// Returns whether anything changed. Only whitespace is touched, so
+    /// the compilation result stays valid.
+    pub fn format(&mut self) -> Result<bool, FormatError> {
+        let formatted = format_wgsl(&self.source_code, &self.format_options)?;
+        if formatted == self.source_code {
+            return Ok(false);
+        }
+        self.source_code = formatted;
+        if self.realtime_validation_enabled {
+            self.realtime_validate();
+        }
+        Ok(true)
+    }
+
+    pub fn format_options(&self) -> &FormatOptions {
+        &self.format_options
+    }
+
+    pub fn set_format_options(&mut self, options: FormatOptions) {
+        self.format_options = options;
+    }
+
+    /// Version history of the edited shaders
+    pub fn history(&self) -> &ShaderHistory {
+        &self.history
+    }
+
+    /// Key of the current shader in the history: its file, else its label
+    pub fn history_key(&self) -> &str {
+        if self.file_path.is_empty() {
+            &self.label
+        } else {
+            &self.file_path
+        }
+    }
+
+    /// Save the current source as a named snapshot
+    pub fn save_snapshot(&mut self, name: &str) -> u64 {
+        let key = self.history_key().to_string();
+        self.history.save(&key, name, &self.source_code)
+    }
+
+    /// Replace the source with a snapshot of the current shader
+    ///
+    /// The source being replaced is snapshotted first, unless it is already
+    /// the latest snapshot, so a restore can always be undone.
+    pub fn restore_snapshot(&mut self, id: u64) -> bool {
+        let key = self.history_key().to_string();
+        let Some(source) = self.history.get(&key, id).map(|s| s.source.clone()) else {
+            return false;
+        };
+        self.history.record(&key, &self.source_code);
+        self.set_source_code(source);
+        true
+    }
+
+    /// Source of a version in the diff viewer
+    fn version_source(&self, version: Version) -> Option<&str> {
+        match version {
+            Version::Snapshot(id) => self
+                .history
+                .get(self.history_key(), id)
+                .map(|s| s.source.as_str()),
+            Version::Current => Some(&self.source_code),
+        }
+    }
+
+    fn version_title(&self, version: Version) -> String {
+        match version {
+            Version::Snapshot(id) => self
+                .history
+                .get(self.history_key(), id)
+                .map_or_else(|| "Removed snapshot".to_string(), |s| s.title()),
+            Version::Current => "Current source".to_string(),
+        }
+    }
+
     /// Get the compilation result
     pub fn compilation_result(&self) -> &CompilationResult {
         &self.compilation_result
@@ -147,15 +472,60 @@ fn fs_main() -> @location(0) vec4<f32> {
         &self.validation_errors
     }
 
+    /// Set the device offered as a lint target
+    ///
+    /// Typically the current adapter's capability snapshot, or a snapshot
+    /// loaded as a virtual device.
+    pub fn set_lint_device(&mut self, device: Option<&VirtualDevice>) {
+        let unchanged = match (&self.lint_device, device) {
+            (Some(current), Some(new)) => current.snapshot() == new.snapshot(),
+            (None, None) => true,
+            _ => false,
+        };
+        if unchanged {
+            return;
+        }
+        self.lint_device = device.cloned();
+        if matches!(self.lint_profile, TargetProfile::Device(_)) {
+            self.lint_profile = match &self.lint_device {
+                Some(device) => TargetProfile::Device(device.clone()),
+                None => TargetProfile::WebGpuCore,
+            };
+        }
+        self.lint_results = None;
+    }
+
+    /// Lint the current shader against the selected target profile
+    pub fn lint(&mut self) -> &Result<Vec<LintDiagnostic>, String> {
+        let result = self
+            .composed_source()
+            .and_then(|source| crate::shader_lint::lint_wgsl(&source, &self.lint_profile));
+        self.lint_results.insert(result)
+    }
+
     /// Compile the current shader
     pub fn compile(&mut self, device: &wgpu::Device) {
+        let source = match self.composed_source() {
+            Ok(source) => source,
+            Err(e) => {
+                self.compilation_result = CompilationResult::Error(e);
+                return;
+            }
+        };
         // Try to create a shader module
-        match ShaderModule::from_source(&self.source_code, Some(&self.label)) {
+        match ShaderModule::from_source(&source, Some(&self.label)) {
             Ok(shader) => {
-                // Attempt to compile with wgpu
-                // Note: wgpu's create_module does validation internally
-                let _module = shader.create_module(device);
+                // Naga accepted the source; the device may still reject it,
+                // e.g. for features or limits it does not support
+                let _module = self
+                    .gpu_errors
+                    .scoped(device, COMPILE, || shader.create_module(device));
                 self.compilation_result = CompilationResult::Success;
+                self.take_device_error();
+                if matches!(self.compilation_result, CompilationResult::Success) {
+                    let key = self.history_key().to_string();
+                    self.history.record(&key, &self.source_code);
+                }
             }
             Err(e) => {
                 self.compilation_result = CompilationResult::Error(format!("{}", e));
@@ -163,9 +533,40 @@ fn fs_main() -> @location(0) vec4<f32> {
         }
     }
 
+    /// Turn a device error from the last compile into the compilation result
+    ///
+    /// On the web the error arrives after `compile` returned, so this is
+    /// checked again every frame.
+    fn take_device_error(&mut self) {
+        if let Some(error) = self.gpu_errors.get(COMPILE) {
+            self.gpu_errors.clear(COMPILE);
+            self.compilation_result = CompilationResult::Error(error.message);
+        }
+    }
+
+    /// Benchmark compiling the current shader on the device
+    pub fn run_benchmark(&mut self, device: &wgpu::Device) -> &Result<BenchmarkReport, String> {
+        let result = self
+            .composed_source()
+            .and_then(|source| benchmark_compile(device, &source, &self.benchmark_config));
+        self.benchmark_result.insert(result)
+    }
+
+    /// Result of the last compile benchmark
+    pub fn benchmark_result(&self) -> Option<&Result<BenchmarkReport, String>> {
+        self.benchmark_result.as_ref()
+    }
+
     /// Validate shader syntax (compilation without creating module)
     pub fn validate(&mut self) -> bool {
-        match ShaderModule::from_source(&self.source_code, Some(&self.label)) {
+        let source = match self.composed_source() {
+            Ok(source) => source,
+            Err(e) => {
+                self.compilation_result = CompilationResult::Error(e);
+                return false;
+            }
+        };
+        match ShaderModule::from_source(&source, Some(&self.label)) {
             Ok(_) => {
                 // Basic validation passed
                 true
@@ -187,8 +588,51 @@ fn fs_main() -> @location(0) vec4<f32> {
             return;
         }
 
+        // Resolve includes and then imports first; their errors point at
+        // the directive
+        let expanded = match self.expand() {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                let name = shader_name(self.shader_path().as_deref());
+                let line = match e.location() {
+                    Some((file, line)) if file == name => line,
+                    _ => 1,
+                };
+                self.validation_errors.push(ValidationError {
+                    message: e.to_string(),
+                    line,
+                    column: None,
+                });
+                return;
+            }
+        };
+        let expanded_source = expanded
+            .as_ref()
+            .map_or(self.source_code.as_str(), |expanded| {
+                expanded.source.as_str()
+            });
+        let composed = match self.compose(expanded_source) {
+            Ok(composed) => composed,
+            Err(e) => {
+                let line = match e.location() {
+                    Some((None, line)) => line,
+                    _ => 1,
+                };
+                let (message, line) = include_origin(expanded.as_ref(), e.to_string(), line);
+                self.validation_errors.push(ValidationError {
+                    message,
+                    line,
+                    column: None,
+                });
+                return;
+            }
+        };
+        let source = composed
+            .as_ref()
+            .map_or(expanded_source, |composed| composed.source.as_str());
+
         // Use naga to parse and validate WGSL
-        match naga::front::wgsl::parse_str(&self.source_code) {
+        match naga::front::wgsl::parse_str(source) {
             Ok(_module) => {
                 // Validation successful
                 log::trace!("Real-time validation: OK");
@@ -205,17 +649,19 @@ fn fs_main() -> @location(0) vec4<f32> {
                 for line in error_message.lines() {
                     // Check for line number patterns
                     if let Some(line_num) = self.extract_line_number(line) {
-                        self.validation_errors.push(ValidationError {
-                            message: line.trim().to_string(),
-                            line: line_num,
-                            column: None,
-                        });
+                        self.validation_errors.push(editor_error(
+                            expanded.as_ref(),
+                            composed.as_ref(),
+                            line.trim().to_string(),
+                            line_num,
+                        ));
                         found_location = true;
                         break;
                     }
                 }
 
-                // If we couldn't extract location, add error at line 1
+                // If we couldn't extract location, use the error's span,
+                // or line 1 without one
                 if !found_location {
                     // Get first meaningful error line
                     let msg = error_message
@@ -225,11 +671,15 @@ fn fs_main() -> @location(0) vec4<f32> {
                         .trim()
                         .to_string();
 
-                    self.validation_errors.push(ValidationError {
-                        message: msg,
-                        line: 1,
-                        column: None,
-                    });
+                    let line = parse_error
+                        .location(source)
+                        .map_or(1, |location| location.line_number as usize);
+                    self.validation_errors.push(editor_error(
+                        expanded.as_ref(),
+                        composed.as_ref(),
+                        msg,
+                        line,
+                    ));
                 }
             }
         }
@@ -272,6 +722,27 @@ fn fs_main() -> @location(0) vec4<f32> {
 
     /// Render the shader editor UI
     pub fn ui(&mut self, ui: &mut egui::Ui, device: Option<&wgpu::Device>) {
+        self.render_editor_ui(ui, device);
+        self.render_tools_ui(ui);
+    }
+
+    /// Render the shader editor UI with a live preview and widgets for the
+    /// shader's uniforms (Native version)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ui_with_preview(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        self.render_editor_ui(ui, Some(device));
+        self.render_live_uniforms_ui(ui, device, queue, renderer);
+        self.render_tools_ui(ui);
+    }
+
+    /// Render the controls, the code and its validation errors
+    fn render_editor_ui(&mut self, ui: &mut egui::Ui, device: Option<&wgpu::Device>) {
         // Check for shader file changes if hot reload is enabled
         if self.hot_reload_enabled && !self.file_path.is_empty() {
             if let Some(watcher) = &self.shader_watcher {
@@ -291,6 +762,13 @@ fn fs_main() -> @location(0) vec4<f32> {
                         let path = self.file_path.clone();
                         self.load_from_file(&path);
                         ui.ctx().request_repaint(); // Request UI repaint
+                    } else if event.filename.starts_with(LIBRARY_PREFIX) {
+                        log::info!(
+                            "Hot reload: Shader module '{}' changed, reloading modules...",
+                            event.filename
+                        );
+                        self.reload_modules();
+                        ui.ctx().request_repaint();
                     }
                 }
             }
@@ -333,8 +811,47 @@ fn fs_main() -> @location(0) vec4<f32> {
                 ui.add_enabled(false, egui::Button::new("⚙️ Compile (No Device)"));
             }
 
+            if ui
+                .add_enabled(device.is_some(), egui::Button::new("⏱ Benchmark compile"))
+                .on_hover_text(
+                    "Compile the shader repeatedly and compare naga front-end and driver timings",
+                )
+                .clicked()
+            {
+                if let Some(dev) = device {
+                    self.run_benchmark(dev);
+                }
+            }
+            ui.add(
+                egui::DragValue::new(&mut self.benchmark_config.iterations)
+                    .range(1..=MAX_ITERATIONS)
+                    .suffix("×"),
+            )
+            .on_hover_text("Benchmark iterations");
+
+            ui.separator();
+
+            if ui
+                .button("✨ Format")
+                .on_hover_text("Reindent the shader and normalize its spacing")
+                .clicked()
+            {
+                if let Err(e) = self.format() {
+                    self.compilation_result =
+                        CompilationResult::Error(format!("Failed to format: {}", e));
+                }
+            }
+            ui.add(
+                egui::DragValue::new(&mut self.format_options.indent_width)
+                    .range(1..=MAX_INDENT_WIDTH)
+                    .suffix(" spaces"),
+            )
+            .on_hover_text("Indent width");
+
             // Reset button
             if ui.button("🔄 Reset").clicked() {
+                let key = self.history_key().to_string();
+                self.history.record(&key, &self.source_code);
                 self.source_code = Self::default_shader_code();
                 self.compilation_result = CompilationResult::NotCompiled;
             }
@@ -343,6 +860,7 @@ fn fs_main() -> @location(0) vec4<f32> {
         ui.add_space(10.0);
 
         // Compilation result display
+        self.take_device_error();
         match &self.compilation_result {
             CompilationResult::Success => {
                 ui.colored_label(egui::Color32::GREEN, "✅ Compilation successful!");
@@ -355,6 +873,8 @@ fn fs_main() -> @location(0) vec4<f32> {
             }
         }
 
+        self.render_benchmark_ui(ui);
+
         ui.add_space(10.0);
         ui.separator();
 
@@ -423,7 +943,17 @@ fn fs_main() -> @location(0) vec4<f32> {
                 "✅ No validation errors",
             );
         }
+    }
 
+    /// Render the import, layout, lint and option sections below the code
+    fn render_tools_ui(&mut self, ui: &mut egui::Ui) {
+        self.render_imports_ui(ui);
+        self.render_layout_check_ui(ui);
+
+        ui.add_space(10.0);
+        self.render_lint_ui(ui);
+        ui.add_space(10.0);
+        self.render_history_ui(ui);
         ui.add_space(10.0);
 
         // Options
@@ -473,6 +1003,570 @@ fn fs_main() -> @location(0) vec4<f32> {
         });
     }
 
+    /// Render the live preview next to widgets for every uniform field
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_live_uniforms_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        ui.add_space(10.0);
+        egui::CollapsingHeader::new("🎚 Live Uniforms")
+            .id_salt("shader_live_uniforms")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.label(
+                    "Every field of the shader's uniform buffers gets a widget. The values are \
+                    written into the bound buffers each frame while the preview draws the first \
+                    @vertex and @fragment entry points.",
+                );
+                self.refresh_preview(device);
+                let Some(preview) = &mut self.preview else {
+                    return;
+                };
+                self.controllers.update(ui.input(|i| i.stable_dt));
+                self.controllers.mapper.apply(&mut self.uniforms);
+                for (target, _, value) in self.controllers.mapper.outputs() {
+                    if target == VERTICES_TARGET {
+                        preview.vertex_count = value.round().clamp(1.0, 1_000_000.0) as u32;
+                    }
+                }
+                preview.render(device, queue, &self.uniforms);
+
+                ui.horizontal_top(|ui| {
+                    let texture_id = preview.texture_id(device, renderer);
+                    let size = egui::vec2(PREVIEW_SIZE as f32, PREVIEW_SIZE as f32);
+                    let response = ui.image(egui::load::SizedTexture::new(texture_id, size));
+                    self.pixel_picker.image_hover_ui(
+                        ui,
+                        &response,
+                        device,
+                        queue,
+                        preview.texture(),
+                    );
+                    ui.vertical(|ui| {
+                        if let Some(error) = preview.error() {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(220, 50, 50),
+                                format!("❌ {}", error),
+                            );
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label("Vertices:");
+                            ui.add(
+                                egui::DragValue::new(&mut preview.vertex_count)
+                                    .range(1..=1_000_000),
+                            );
+                        });
+                        ui.separator();
+                        self.uniforms.ui(ui);
+                    });
+                });
+
+                egui::CollapsingHeader::new("🎮 Controllers")
+                    .id_salt("shader_live_controllers")
+                    .show(ui, |ui| {
+                        let mut targets =
+                            controller_input::uniform_targets(&self.uniforms, |_| true);
+                        targets.push(MappingTarget {
+                            name: VERTICES_TARGET.to_string(),
+                            components: 1,
+                        });
+                        self.controllers.ui(ui, &targets);
+                    });
+                ui.ctx().request_repaint();
+            });
+    }
+
+    /// Render the compatibility lint controls and results
+    fn render_benchmark_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(result) = &self.benchmark_result else {
+            return;
+        };
+        ui.add_space(6.0);
+        let mut close = false;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("⏱ Compile Benchmark");
+                if ui
+                    .small_button("✖")
+                    .on_hover_text("Clear results")
+                    .clicked()
+                {
+                    close = true;
+                }
+            });
+            let report = match result {
+                Ok(report) => report,
+                Err(e) => {
+                    ui.colored_label(egui::Color32::RED, format!("❌ {}", e));
+                    return;
+                }
+            };
+            ui.label(format!(
+                "{} iterations, {}; total cold compile {}",
+                report.iterations,
+                report
+                    .pipeline_target
+                    .as_ref()
+                    .map_or("no pipeline".to_string(), |t| t.to_string()),
+                format_ms(report.total_cold())
+            ));
+
+            egui::Grid::new("shader_benchmark_results")
+                .num_columns(7)
+                .spacing([14.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in [
+                        "Stage",
+                        "Cold",
+                        "Warm mean",
+                        "Median",
+                        "Min",
+                        "Max",
+                        "Std dev",
+                    ] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+
+                    let row = |ui: &mut egui::Ui, stage: &str, timing: &PhaseTiming| {
+                        ui.label(stage);
+                        ui.label(format_ms(timing.cold));
+                        match &timing.warm {
+                            Some(warm) => {
+                                for value in
+                                    [warm.mean, warm.median, warm.min, warm.max, warm.std_dev]
+                                {
+                                    ui.label(format_ms(value));
+                                }
+                            }
+                            None => {
+                                for _ in 0..5 {
+                                    ui.label("—");
+                                }
+                            }
+                        }
+                        ui.end_row();
+                    };
+                    row(ui, "Front end (naga)", &report.front_end);
+                    row(ui, "Shader module", &report.shader_module);
+                    if let Some(pipeline) = &report.pipeline {
+                        row(ui, "Pipeline (driver)", pipeline);
+                    }
+                });
+        });
+        if close {
+            self.benchmark_result = None;
+        }
+    }
+
+    fn render_lint_ui(&mut self, ui: &mut egui::Ui) {
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("🔍 Lint against:");
+            let previous = self.lint_profile.name();
+            egui::ComboBox::from_id_salt("shader_lint_profile")
+                .selected_text(&previous)
+                .show_ui(ui, |ui| {
+                    if ui
+                        .selectable_label(
+                            matches!(self.lint_profile, TargetProfile::WebGpuCore),
+                            TargetProfile::WebGpuCore.name(),
+                        )
+                        .clicked()
+                    {
+                        self.lint_profile = TargetProfile::WebGpuCore;
+                    }
+                    if ui
+                        .selectable_label(
+                            matches!(self.lint_profile, TargetProfile::Compatibility),
+                            TargetProfile::Compatibility.name(),
+                        )
+                        .clicked()
+                    {
+                        self.lint_profile = TargetProfile::Compatibility;
+                    }
+                    if let Some(device) = &self.lint_device {
+                        let profile = TargetProfile::Device(device.clone());
+                        if ui
+                            .selectable_label(
+                                matches!(self.lint_profile, TargetProfile::Device(_)),
+                                profile.name(),
+                            )
+                            .clicked()
+                        {
+                            self.lint_profile = profile;
+                        }
+                    }
+                });
+            if self.lint_profile.name() != previous {
+                self.lint_results = None;
+            }
+
+            if ui.button("▶ Run Lint").clicked() {
+                self.lint();
+            }
+        });
+
+        match &self.lint_results {
+            Some(Ok(diagnostics)) if diagnostics.is_empty() => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(50, 200, 50),
+                    format!("✅ No issues for {}", self.lint_profile.name()),
+                );
+            }
+            Some(Ok(diagnostics)) => {
+                egui::ScrollArea::vertical()
+                    .id_salt("shader_lint_results")
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        for diagnostic in diagnostics {
+                            let color = match diagnostic.severity {
+                                LintSeverity::Error => egui::Color32::from_rgb(255, 100, 100),
+                                LintSeverity::Warning => egui::Color32::from_rgb(255, 200, 80),
+                            };
+                            ui.colored_label(color, diagnostic.to_string());
+                        }
+                    });
+            }
+            Some(Err(message)) => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 50, 50),
+                    format!("❌ Shader does not validate: {}", message),
+                );
+            }
+            None => {
+                ui.label("ℹ️ Run the linter to check the shader against the selected target.");
+            }
+        }
+    }
+
+    /// Render the snapshots of the current shader and the diff viewer
+    fn render_history_ui(&mut self, ui: &mut egui::Ui) {
+        let key = self.history_key().to_string();
+        let count = self.history.snapshots(&key).len();
+        let mut action = None;
+        egui::CollapsingHeader::new(format!("🕘 History ({})", count))
+            .id_salt("shader_history")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut self.snapshot_name);
+                    if ui
+                        .button("📸 Save snapshot")
+                        .on_hover_text("Snapshots are also taken after every successful compile")
+                        .clicked()
+                    {
+                        let name = std::mem::take(&mut self.snapshot_name);
+                        self.save_snapshot(&name);
+                    }
+                });
+
+                if count == 0 {
+                    ui.label("ℹ️ No snapshots of this shader yet.");
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .id_salt("shader_history_list")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("shader_history_grid")
+                            .num_columns(4)
+                            .striped(true)
+                            .spacing([10.0, 4.0])
+                            .show(ui, |ui| action = self.history_rows_ui(ui, &key));
+                    });
+
+                self.render_history_diff_ui(ui, &key);
+            });
+        match action {
+            Some(HistoryAction::Diff(id)) => {
+                self.history_diff = Some((Version::Snapshot(id), Version::Current));
+            }
+            Some(HistoryAction::Restore(id)) => {
+                self.restore_snapshot(id);
+            }
+            Some(HistoryAction::Remove(id)) => {
+                self.history.remove(&key, id);
+            }
+            None => {}
+        }
+    }
+
+    /// Render one grid row per snapshot, newest first
+    fn history_rows_ui(&self, ui: &mut egui::Ui, key: &str) -> Option<HistoryAction> {
+        let mut action = None;
+        for snapshot in self.history.snapshots(key).iter().rev() {
+            if snapshot.is_automatic() {
+                ui.label(snapshot.title());
+            } else {
+                ui.strong(snapshot.title());
+            }
+            ui.weak(snapshot.time_label());
+            let (added, removed) = diff_stats(&diff_lines(&snapshot.source, &self.source_code));
+            if added + removed == 0 {
+                ui.weak("same as current");
+            } else {
+                ui.weak(format!("+{} −{} to current", added, removed));
+            }
+            ui.horizontal(|ui| {
+                if ui.small_button("Diff").clicked() {
+                    action = Some(HistoryAction::Diff(snapshot.id));
+                }
+                if ui
+                    .small_button("↩ Restore")
+                    .on_hover_text("Replace the source; the current source is snapshotted first")
+                    .clicked()
+                {
+                    action = Some(HistoryAction::Restore(snapshot.id));
+                }
+                if ui.small_button("🗑").clicked() {
+                    action = Some(HistoryAction::Remove(snapshot.id));
+                }
+            });
+            ui.end_row();
+        }
+        action
+    }
+
+    /// Render the line diff between the two versions picked for comparison
+    fn render_history_diff_ui(&mut self, ui: &mut egui::Ui, key: &str) {
+        let Some((mut from, mut to)) = self.history_diff else {
+            return;
+        };
+        ui.separator();
+        let mut close = false;
+        ui.horizontal(|ui| {
+            ui.label("Compare");
+            self.version_combo(ui, "shader_history_from", key, &mut from);
+            ui.label("with");
+            self.version_combo(ui, "shader_history_to", key, &mut to);
+            close = ui.button("✖ Close").clicked();
+        });
+        if close {
+            self.history_diff = None;
+            return;
+        }
+        self.history_diff = Some((from, to));
+
+        let (Some(old), Some(new)) = (self.version_source(from), self.version_source(to)) else {
+            ui.label("ℹ️ The snapshot was removed.");
+            return;
+        };
+        let diff = diff_lines(old, new);
+        let (added, removed) = diff_stats(&diff);
+        if added + removed == 0 {
+            ui.label("✅ The versions are identical.");
+            return;
+        }
+        ui.label(format!("{} line(s) added, {} removed", added, removed));
+        egui::ScrollArea::vertical()
+            .id_salt("shader_history_diff")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (index, hunk) in hunks(&diff, DIFF_CONTEXT).into_iter().enumerate() {
+                    if index > 0 || hunk.start > 0 {
+                        ui.weak("⋯");
+                    }
+                    for line in &diff[hunk] {
+                        let (sign, number, color) = match line.kind {
+                            DiffKind::Same => (' ', line.new_line, ui.visuals().text_color()),
+                            DiffKind::Added => {
+                                ('+', line.new_line, egui::Color32::from_rgb(100, 220, 100))
+                            }
+                            DiffKind::Removed => {
+                                ('-', line.old_line, egui::Color32::from_rgb(255, 100, 100))
+                            }
+                        };
+                        ui.colored_label(
+                            color,
+                            egui::RichText::new(format!(
+                                "{:>4} {} {}",
+                                number.unwrap_or_default(),
+                                sign,
+                                line.text
+                            ))
+                            .monospace(),
+                        );
+                    }
+                }
+            });
+    }
+
+    /// Combo box picking one of the snapshots or the current source
+    fn version_combo(&self, ui: &mut egui::Ui, id: &str, key: &str, version: &mut Version) {
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(self.version_title(*version))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(version, Version::Current, "Current source");
+                for snapshot in self.history.snapshots(key).iter().rev() {
+                    ui.selectable_value(
+                        version,
+                        Version::Snapshot(snapshot.id),
+                        format!("{} ({})", snapshot.title(), snapshot.time_label()),
+                    );
+                }
+            });
+    }
+
+    /// Render the modules the shader imports
+    fn render_imports_ui(&mut self, ui: &mut egui::Ui) {
+        if !has_imports(&self.source_code) {
+            return;
+        }
+        ui.add_space(10.0);
+        let mut reload = false;
+        egui::CollapsingHeader::new("📦 Imports")
+            .id_salt("shader_imports")
+            .default_open(true)
+            .show(ui, |ui| {
+                match self.composer.import_graph(&self.source_code) {
+                    Ok(graph) => {
+                        draw_import_graph(ui, &graph);
+                        egui::Grid::new("shader_import_items")
+                            .num_columns(2)
+                            .spacing([14.0, 4.0])
+                            .show(ui, |ui| {
+                                for node in graph.nodes.iter().skip(1) {
+                                    ui.monospace(&node.name);
+                                    ui.label(node.items.join(", "));
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::from_rgb(220, 50, 50), format!("❌ {}", e));
+                    }
+                }
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} module(s) available",
+                        self.composer.modules().count()
+                    ));
+                    if ui
+                        .button("🔄 Reload modules")
+                        .on_hover_text("Re-read the lib_*.wgsl files in the shaders directories")
+                        .clicked()
+                    {
+                        reload = true;
+                    }
+                });
+            });
+        if reload {
+            self.reload_modules();
+        }
+    }
+
+    /// Render the CPU/shader buffer layout comparison
+    fn render_layout_check_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(10.0);
+        let mut check = None;
+        egui::CollapsingHeader::new("📐 Buffer Layout Check")
+            .id_salt("shader_layout_check")
+            .show(ui, |ui| {
+                ui.label(
+                    "Paste the #[repr(C)] struct you upload to a uniform or storage buffer to \
+                    compare its field offsets and sizes with the shader's layout.",
+                );
+                let bindings = match self.buffer_bindings() {
+                    Ok(bindings) if bindings.is_empty() => {
+                        ui.label("ℹ️ The shader has no uniform or storage buffers.");
+                        return;
+                    }
+                    Ok(bindings) => bindings,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::from_rgb(220, 50, 50), format!("❌ {}", e));
+                        return;
+                    }
+                };
+                let selected = self
+                    .layout_binding
+                    .filter(|&(group, binding)| {
+                        bindings
+                            .iter()
+                            .any(|b| b.group == group && b.binding == binding)
+                    })
+                    .or_else(|| {
+                        bindings
+                            .iter()
+                            .find(|b| b.layout.is_ok())
+                            .or(bindings.first())
+                            .map(|b| (b.group, b.binding))
+                    });
+                self.layout_binding = selected;
+
+                ui.horizontal(|ui| {
+                    ui.label("Buffer:");
+                    let selected_label = bindings
+                        .iter()
+                        .find(|b| Some((b.group, b.binding)) == selected)
+                        .map(ShaderBufferBinding::label)
+                        .unwrap_or_default();
+                    egui::ComboBox::from_id_salt("shader_layout_binding")
+                        .selected_text(selected_label)
+                        .show_ui(ui, |ui| {
+                            for binding in &bindings {
+                                let key = (binding.group, binding.binding);
+                                if ui
+                                    .selectable_label(selected == Some(key), binding.label())
+                                    .clicked()
+                                {
+                                    self.layout_binding = Some(key);
+                                    self.layout_check = None;
+                                }
+                            }
+                        });
+                    if ui.button("▶ Check Layout").clicked() {
+                        check = self.layout_binding;
+                    }
+                });
+
+                if ui
+                    .add(
+                        egui::TextEdit::multiline(&mut self.layout_cpu_struct)
+                            .code_editor()
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(6),
+                    )
+                    .changed()
+                {
+                    self.layout_check = None;
+                }
+
+                match &self.layout_check {
+                    Some(Ok(mismatches)) if mismatches.is_empty() => {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(50, 200, 50),
+                            "✅ CPU and shader layouts match",
+                        );
+                    }
+                    Some(Ok(mismatches)) => {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(220, 50, 50),
+                            format!("⚠️ {} layout mismatch(es):", mismatches.len()),
+                        );
+                        for mismatch in mismatches {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 100, 100),
+                                format!("  • {}", mismatch),
+                            );
+                        }
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::from_rgb(220, 50, 50), format!("❌ {}", e));
+                    }
+                    None => {}
+                }
+            });
+        if let Some((group, binding)) = check {
+            self.check_buffer_layout(group, binding);
+        }
+    }
+
     /// Render editor with line numbers
     /// Returns true if the text was changed
     fn render_with_line_numbers(&mut self, ui: &mut egui::Ui) -> bool {
@@ -691,6 +1785,7 @@ fn fs_main() -> @location(0) vec4<f32> {
             source_code: self.source_code.clone(),
             label: self.label.clone(),
             file_path: self.file_path.clone(),
+            history: self.history.clone(),
         }
     }
 
@@ -699,10 +1794,135 @@ fn fs_main() -> @location(0) vec4<f32> {
         self.source_code = state.source_code.clone();
         self.label = state.label.clone();
         self.file_path = state.file_path.clone();
+        self.history = state.history.clone();
+        self.history_diff = None;
         self.compilation_result = CompilationResult::NotCompiled;
     }
 }
 
+/// Validation error for a line of the composed shader, in editor lines
+///
+/// Errors inside an imported module are shown on the first line and name
+/// the module.
+fn editor_error(
+    expanded: Option<&ExpandedShader>,
+    composed: Option<&ComposedShader>,
+    message: String,
+    line: usize,
+) -> ValidationError {
+    let (message, line) = match composed.map(|composed| composed.locate(line)) {
+        Some(Some((Some(module), module_line))) => (
+            format!("In module {}, line {}: {}", module, module_line, message),
+            1,
+        ),
+        Some(Some((None, line))) | None => include_origin(expanded, message, line),
+        Some(None) => (message, line),
+    };
+    ValidationError {
+        message,
+        line,
+        column: None,
+    }
+}
+
+/// Message and editor line for a line of the shader with its includes
+/// pasted in
+///
+/// Errors inside an included file are shown on the `#include` they come
+/// through and name the file.
+fn include_origin(
+    expanded: Option<&ExpandedShader>,
+    message: String,
+    line: usize,
+) -> (String, usize) {
+    match expanded.and_then(|expanded| expanded.locate(line)) {
+        Some(origin) if origin.included => (
+            format!("In {}, line {}: {}", origin.file, origin.line, message),
+            origin.root_line,
+        ),
+        Some(origin) => (message, origin.line),
+        None => (message, line),
+    }
+}
+
+/// Draw an import graph as layers of boxes, importers above their imports
+fn draw_import_graph(ui: &mut egui::Ui, graph: &ImportGraph) {
+    const NODE_HEIGHT: f32 = 24.0;
+    const NODE_PADDING: f32 = 16.0;
+    const NODE_GAP: f32 = 16.0;
+    const LAYER_GAP: f32 = 32.0;
+
+    let text_color = ui.visuals().text_color();
+    let galleys: Vec<_> = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            ui.painter().layout_no_wrap(
+                node.name.clone(),
+                egui::FontId::monospace(12.0),
+                text_color,
+            )
+        })
+        .collect();
+    let widths: Vec<f32> = galleys
+        .iter()
+        .map(|galley| galley.size().x + NODE_PADDING)
+        .collect();
+
+    let layers = graph.nodes.iter().map(|node| node.depth).max().unwrap_or(0) + 1;
+    let mut layer_widths = vec![-NODE_GAP; layers];
+    for (node, width) in graph.nodes.iter().zip(&widths) {
+        layer_widths[node.depth] += width + NODE_GAP;
+    }
+    let graph_width = layer_widths.iter().copied().fold(0.0, f32::max);
+    let graph_height = layers as f32 * (NODE_HEIGHT + LAYER_GAP) - LAYER_GAP;
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(ui.available_width().max(graph_width), graph_height),
+        egui::Sense::hover(),
+    );
+    let area = response.rect;
+
+    let mut next_x: Vec<f32> = layer_widths
+        .iter()
+        .map(|width| area.center().x - width / 2.0)
+        .collect();
+    let rects: Vec<egui::Rect> = graph
+        .nodes
+        .iter()
+        .zip(&widths)
+        .map(|(node, &width)| {
+            let x = next_x[node.depth];
+            next_x[node.depth] += width + NODE_GAP;
+            let y = area.top() + node.depth as f32 * (NODE_HEIGHT + LAYER_GAP);
+            egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(width, NODE_HEIGHT))
+        })
+        .collect();
+
+    let edge_stroke = egui::Stroke::new(1.5, ui.visuals().weak_text_color());
+    for &(from, to) in &graph.edges {
+        let start = rects[from].center_bottom();
+        let end = rects[to].center_top();
+        painter.arrow(start, end - start, edge_stroke);
+    }
+
+    for (index, (rect, galley)) in rects.iter().zip(galleys).enumerate() {
+        // The shader itself stands out from its modules
+        let color = if index == 0 {
+            egui::Color32::from_rgb(100, 150, 255)
+        } else {
+            egui::Color32::from_rgb(120, 200, 120)
+        };
+        painter.rect_filled(*rect, 4.0, color.linear_multiply(0.25));
+        painter.rect_stroke(
+            *rect,
+            4.0,
+            egui::Stroke::new(1.0, color),
+            egui::epaint::StrokeKind::Inside,
+        );
+        painter.galley(rect.center() - galley.size() / 2.0, galley, text_color);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -820,6 +2040,136 @@ mod tests {
         assert!(!editor.validation_errors()[0].message.is_empty());
     }
 
+    #[test]
+    fn test_realtime_validation_with_imports() {
+        let mut editor = ShaderEditor::new();
+        editor.composer = ShaderComposer::new();
+        editor
+            .composer
+            .add_module("#define_import_path test::math\nconst HALF: f32 = 0.5;")
+            .unwrap();
+
+        editor.set_source_code(
+            "#import test::math\n\nfn f() -> f32 { return math::HALF; }".to_string(),
+        );
+        assert!(editor.validation_errors().is_empty());
+
+        // Errors in the shader keep their editor line
+        editor.set_source_code(
+            "#import test::math\n\nfn f() -> f32 { return math::HALF }".to_string(),
+        );
+        assert_eq!(editor.validation_errors()[0].line, 3);
+
+        editor.set_source_code("\n#import test::missing".to_string());
+        assert_eq!(editor.validation_errors()[0].line, 2);
+        assert!(editor.validation_errors()[0]
+            .message
+            .contains("test::missing"));
+    }
+
+    #[test]
+    fn test_realtime_validation_with_includes() {
+        let mut editor = ShaderEditor::new();
+        editor.includes = IncludeResolver::new();
+        editor
+            .includes
+            .add_file("half.wgsl", "// Shared constants\nconst HALF: f32 = 0.5;\n");
+        editor
+            .includes
+            .add_file("broken.wgsl", "\nfn broken() -> f32 { return HALF }\n");
+
+        editor.set_source_code(
+            "#include \"half.wgsl\"\n\nfn f() -> f32 { return HALF; }".to_string(),
+        );
+        assert!(editor.validation_errors().is_empty());
+
+        // Errors after an include keep their editor line
+        editor
+            .set_source_code("#include \"half.wgsl\"\n\nfn f() -> f32 { return HALF }".to_string());
+        assert_eq!(editor.validation_errors()[0].line, 3);
+
+        // Errors inside an include are shown on its directive
+        editor.set_source_code("#include \"half.wgsl\"\n#include \"broken.wgsl\"\n".to_string());
+        assert_eq!(editor.validation_errors()[0].line, 2);
+        assert!(editor.validation_errors()[0]
+            .message
+            .starts_with("In broken.wgsl, line 2:"));
+
+        editor.set_source_code("\n#include \"missing.wgsl\"".to_string());
+        assert_eq!(editor.validation_errors()[0].line, 2);
+        assert!(editor.validation_errors()[0]
+            .message
+            .contains("missing.wgsl"));
+    }
+
+    #[test]
+    fn test_format_keeps_comments() {
+        let mut editor = ShaderEditor::new();
+        editor.set_format_options(FormatOptions { indent_width: 2 });
+        editor.set_source_code(
+            "// Solid color\n@fragment\nfn fs_main()->@location(0) vec4<f32>{return vec4<f32>(1.0);}"
+                .to_string(),
+        );
+
+        assert_eq!(editor.format(), Ok(true));
+        assert_eq!(
+            editor.source_code(),
+            "// Solid color\n@fragment\nfn fs_main() -> @location(0) vec4<f32> {\n  return vec4<f32>(1.0);\n}\n"
+        );
+        assert!(editor.validation_errors().is_empty());
+        assert_eq!(editor.format(), Ok(false));
+
+        editor.set_source_code("/* unterminated".to_string());
+        assert!(editor.format().is_err());
+        assert_eq!(editor.source_code(), "/* unterminated");
+    }
+
+    #[test]
+    fn test_restore_snapshot_keeps_current_source() {
+        let mut editor = ShaderEditor::new();
+        editor.file_path = "history.wgsl".to_string();
+        editor.set_source_code("fn first() {}\n".to_string());
+        let first = editor.save_snapshot("First");
+        editor.set_source_code("fn second() {}\n".to_string());
+
+        assert!(editor.restore_snapshot(first));
+        assert_eq!(editor.source_code(), "fn first() {}\n");
+        let snapshots = editor.history().snapshots("history.wgsl");
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[1].source, "fn second() {}\n");
+        assert!(snapshots[1].is_automatic());
+        assert!(!editor.restore_snapshot(first + 10));
+
+        // The history travels with the project state
+        let state = editor.export_state();
+        let mut loaded = ShaderEditor::new();
+        loaded.import_state(&state);
+        assert_eq!(loaded.history(), editor.history());
+        assert_eq!(loaded.history_key(), "history.wgsl");
+    }
+
+    #[test]
+    fn test_check_buffer_layout() {
+        let mut editor = ShaderEditor::new();
+        editor.set_source_code(
+            "struct Uniforms { color: vec3<f32>, time: f32, tint: vec3<f32> }\n\
+             @group(0) @binding(0) var<uniform> uniforms: Uniforms;"
+                .to_string(),
+        );
+        assert_eq!(editor.buffer_bindings().unwrap().len(), 1);
+
+        editor.set_layout_cpu_struct(
+            "struct Uniforms { color: [f32; 3], time: f32, tint: [f32; 3] }".to_string(),
+        );
+        let mismatches = editor.check_buffer_layout(0, 0).clone().unwrap();
+        assert_eq!(
+            mismatches[0].to_string(),
+            "struct size 28 on CPU vs 32 in shader"
+        );
+
+        assert!(editor.check_buffer_layout(1, 0).is_err());
+    }
+
     #[test]
     fn test_realtime_validation_empty_shader() {
         let mut editor = ShaderEditor::new();
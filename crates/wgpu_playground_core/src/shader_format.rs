@@ -0,0 +1,633 @@
+//! Token-based WGSL formatter
+//!
+//! Reindents a shader and normalizes the spacing between tokens without
+//! parsing it, so it works on shaders that don't compile yet and keeps
+//! every comment and `#` directive. Braces open on the line of the item
+//! they belong to, statements and struct fields get a line each, and the
+//! line breaks the author put inside an expression or a parameter list are
+//! kept, indented one level per open parenthesis. Runs of blank lines are
+//! shortened to one.
+//!
+//! Only whitespace changes: the result is tokenized again and compared
+//! with the input, and the shader is left alone if anything else differs.
+
+use std::fmt;
+
+/// Widest indent [`FormatOptions::indent_width`] may ask for
+pub const MAX_INDENT_WIDTH: usize = 8;
+
+/// How [`format_wgsl`] lays out a shader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Spaces per indentation level
+    pub indent_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent_width: 4 }
+    }
+}
+
+/// Errors that can occur while formatting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    /// A `/*` comment is never closed
+    UnterminatedComment { line: usize },
+    /// Formatting would have changed more than whitespace
+    TokensChanged,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::UnterminatedComment { line } => {
+                write!(f, "Line {}: block comment is never closed", line)
+            }
+            FormatError::TokensChanged => {
+                write!(f, "Formatting would change the shader's tokens")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Word,
+    Number,
+    Punct,
+    LineComment,
+    BlockComment,
+    /// A whole `#import`, `#include`, `#ifdef`, ... line
+    Directive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Template {
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    /// Line breaks between the previous token and this one
+    newlines_before: usize,
+    /// Any whitespace between the previous token and this one
+    space_before: bool,
+    /// `<` or `>` delimiting template arguments, as in `vec4<f32>`
+    template: Option<Template>,
+}
+
+impl Token<'_> {
+    fn is(&self, text: &str) -> bool {
+        self.kind == Kind::Punct && self.text == text
+    }
+
+    fn is_comment(&self) -> bool {
+        matches!(self.kind, Kind::LineComment | Kind::BlockComment)
+    }
+}
+
+/// Operators of two characters; `<` and `>` combinations are merged after
+/// template arguments have been found
+const TWO_CHAR_OPS: [&str; 16] = [
+    "->", "==", "!=", "&&", "||", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "::",
+];
+
+/// Keywords followed by a space before `(`
+const CONTROL_KEYWORDS: [&str; 6] = ["if", "for", "while", "switch", "return", "else"];
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>, FormatError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut newlines = 0;
+    let mut space = false;
+    let mut line_start = true;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if byte == b'\n' {
+            newlines += 1;
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if byte.is_ascii_whitespace() {
+            space = true;
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let rest = &source[i..];
+        let kind = if byte == b'#' && line_start {
+            i += rest.find('\n').unwrap_or(rest.len());
+            Kind::Directive
+        } else if rest.starts_with("//") {
+            i += rest.find('\n').unwrap_or(rest.len());
+            Kind::LineComment
+        } else if rest.starts_with("/*") {
+            // Block comments nest in WGSL
+            let mut depth = 0;
+            let start_line = line;
+            loop {
+                let rest = &source[i..];
+                if rest.starts_with("/*") {
+                    depth += 1;
+                    i += 2;
+                } else if rest.starts_with("*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else if let Some(c) = rest.chars().next() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    i += c.len_utf8();
+                } else {
+                    return Err(FormatError::UnterminatedComment { line: start_line });
+                }
+            }
+            Kind::BlockComment
+        } else if byte.is_ascii_digit()
+            || (byte == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+        {
+            let hex = rest.starts_with("0x") || rest.starts_with("0X");
+            i += 1;
+            while i < bytes.len() {
+                let exponent = if hex { b"pP" } else { b"eE" };
+                let signed_exponent =
+                    exponent.contains(&bytes[i - 1]) && matches!(bytes[i], b'+' | b'-');
+                if !signed_exponent && !is_word_byte(bytes[i]) && bytes[i] != b'.' {
+                    break;
+                }
+                i += 1;
+            }
+            Kind::Number
+        } else if is_word_byte(byte) {
+            while i < bytes.len() && is_word_byte(bytes[i]) {
+                i += 1;
+            }
+            Kind::Word
+        } else {
+            let two = rest.get(..2).filter(|two| TWO_CHAR_OPS.contains(two));
+            i += two.map_or_else(|| rest.chars().next().map_or(1, char::len_utf8), str::len);
+            Kind::Punct
+        };
+
+        let mut text = &source[start..i];
+        if kind == Kind::LineComment || kind == Kind::Directive {
+            text = text.trim_end();
+        }
+        tokens.push(Token {
+            kind,
+            text,
+            newlines_before: newlines,
+            space_before: space || newlines > 0,
+            template: None,
+        });
+        newlines = 0;
+        space = false;
+        line_start = false;
+    }
+    Ok(tokens)
+}
+
+/// Mark the `<` and `>` around template arguments
+///
+/// A `<` after a name opens a template list if a matching `>` follows
+/// before anything that can't be part of one, like the WGSL grammar's
+/// template list discovery.
+fn find_templates(tokens: &mut [Token]) {
+    for open in 1..tokens.len() {
+        if !tokens[open].is("<") || tokens[open - 1].kind != Kind::Word {
+            continue;
+        }
+        // `<<` and `<=`
+        if tokens
+            .get(open + 1)
+            .is_some_and(|next| !next.space_before && (next.is("<") || next.is("=")))
+        {
+            continue;
+        }
+        let mut depth = 1;
+        let mut nesting = 0usize;
+        let mut close = None;
+        for j in open + 1..tokens.len() {
+            let token = &tokens[j];
+            if token.is_comment() {
+                continue;
+            }
+            match token.text {
+                "<" if tokens[j - 1].kind == Kind::Word => depth += 1,
+                ">" if nesting == 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = Some(j);
+                        break;
+                    }
+                }
+                "(" | "[" => nesting += 1,
+                ")" | "]" if nesting == 0 => break,
+                ")" | "]" => nesting -= 1,
+                ";" | "{" | "}" | ":" | "=" | "&&" | "||" | "->" | "@" => break,
+                _ if token.kind == Kind::Directive => break,
+                _ => {}
+            }
+        }
+        if let Some(close) = close {
+            tokens[open].template = Some(Template::Open);
+            tokens[close].template = Some(Template::Close);
+        }
+    }
+}
+
+/// Join `<` and `>` outside template lists with the `<`, `>` or `=`
+/// right after them into shift and comparison operators
+fn merge_operators<'a>(source: &'a str, tokens: Vec<Token<'a>>) -> Vec<Token<'a>> {
+    let mut merged: Vec<Token<'a>> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        if let Some(last) = merged.last_mut() {
+            let joinable = last.kind == Kind::Punct
+                && token.kind == Kind::Punct
+                && !token.space_before
+                && last.template.is_none()
+                && token.template.is_none();
+            let combined = matches!(
+                (last.text, token.text),
+                ("<", "<") | (">", ">") | ("<" | ">" | "<<" | ">>", "=")
+            );
+            if joinable && combined {
+                let start = last.text.as_ptr() as usize - source.as_ptr() as usize;
+                last.text = &source[start..start + last.text.len() + token.text.len()];
+                continue;
+            }
+        }
+        merged.push(token);
+    }
+    merged
+}
+
+fn lex(source: &str) -> Result<Vec<Token<'_>>, FormatError> {
+    let mut tokens = tokenize(source)?;
+    find_templates(&mut tokens);
+    Ok(merge_operators(source, tokens))
+}
+
+/// Whether printing `a` and `b` without a space between them would lex as
+/// a different operator
+fn would_merge(a: &str, b: &str) -> bool {
+    let (Some(last), Some(first)) = (a.chars().last(), b.chars().next()) else {
+        return false;
+    };
+    let pair: String = [last, first].iter().collect();
+    TWO_CHAR_OPS.contains(&pair.as_str())
+        || matches!(pair.as_str(), "//" | "/*" | "<<" | ">>" | "<=" | ">=")
+}
+
+/// Whether `token` is a prefix operator given the token before it
+fn is_unary(token: &Token, prev: Option<&Token>) -> bool {
+    match token.text {
+        "!" | "~" => token.kind == Kind::Punct,
+        "-" | "*" | "&" if token.kind == Kind::Punct => match prev {
+            None => true,
+            Some(prev) => match prev.kind {
+                Kind::Punct => {
+                    !(prev.is(")") || prev.is("]") || prev.template == Some(Template::Close))
+                }
+                Kind::Word => matches!(prev.text, "return" | "case"),
+                Kind::Number => false,
+                _ => true,
+            },
+        },
+        _ => false,
+    }
+}
+
+fn wants_space(prev: &Token, prev_unary: bool, token: &Token) -> bool {
+    if token.is_comment() {
+        return true;
+    }
+    if prev_unary && would_merge(prev.text, token.text) {
+        return true;
+    }
+    if token.kind == Kind::Punct
+        && (matches!(
+            token.text,
+            "," | ";" | "." | ")" | "]" | ":" | "::" | "++" | "--"
+        ) || token.template.is_some())
+    {
+        return false;
+    }
+    if prev.is("{") && token.is("}") {
+        return false;
+    }
+    if prev_unary
+        || prev.template == Some(Template::Open)
+        || (prev.kind == Kind::Punct && matches!(prev.text, "(" | "[" | "." | "@" | "::"))
+    {
+        return false;
+    }
+    if token.is("(") || token.is("[") {
+        let callee = match prev.kind {
+            Kind::Word => !CONTROL_KEYWORDS.contains(&prev.text),
+            Kind::Punct => prev.is(")") || prev.is("]") || prev.template == Some(Template::Close),
+            _ => false,
+        };
+        return !callee;
+    }
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Break {
+    /// Whatever the spacing rules say
+    Default,
+    Line,
+}
+
+struct Printer {
+    out: String,
+    indent_width: usize,
+    /// Open braces; `true` for struct bodies, whose fields end with `,`
+    braces: Vec<bool>,
+    /// Open `(` and `[`
+    parens: usize,
+    templates: usize,
+    /// A `struct` keyword was seen and its body hasn't opened yet
+    struct_next: bool,
+    /// Between `case` and the `:` or `{` ending its selectors
+    case_label: bool,
+}
+
+impl Printer {
+    fn newline(&mut self, blank: bool) {
+        let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(trimmed);
+        if self.out.is_empty() {
+            return;
+        }
+        self.out.push('\n');
+        if blank {
+            self.out.push('\n');
+        }
+        let indent = (self.braces.len() + self.parens) * self.indent_width;
+        self.out.extend(std::iter::repeat_n(' ', indent));
+    }
+}
+
+/// Reformat a WGSL shader
+///
+/// Returns the source unchanged in content apart from whitespace, ending
+/// with a single newline.
+pub fn format_wgsl(source: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    let tokens = lex(source)?;
+    let mut printer = Printer {
+        out: String::with_capacity(source.len() + source.len() / 8),
+        indent_width: options.indent_width.min(MAX_INDENT_WIDTH),
+        braces: Vec::new(),
+        parens: 0,
+        templates: 0,
+        struct_next: false,
+        case_label: false,
+    };
+
+    let mut pending = Break::Default;
+    let mut prev_unary = false;
+    for (index, token) in tokens.iter().enumerate() {
+        let prev = index.checked_sub(1).map(|i| &tokens[i]);
+        let next = tokens.get(index + 1);
+        let unary = is_unary(token, prev);
+
+        // Closers leave their level before the line is indented
+        if token.is("}") {
+            printer.braces.pop();
+        } else if token.is(")") || token.is("]") {
+            printer.parens = printer.parens.saturating_sub(1);
+        }
+
+        let line = match prev {
+            None => false,
+            Some(prev) => {
+                let forced = matches!(prev.kind, Kind::LineComment | Kind::Directive)
+                    || token.kind == Kind::Directive;
+                let trailing_comment = token.is_comment() && token.newlines_before == 0;
+                if forced {
+                    true
+                } else if token.is("{") || (token.text == "else" && prev.is("}")) {
+                    false
+                } else if token.is("}") {
+                    !prev.is("{")
+                } else if trailing_comment {
+                    false
+                } else {
+                    pending == Break::Line || token.newlines_before > 0
+                }
+            }
+        };
+        if line {
+            let blank = token.newlines_before > 1
+                && !prev.is_some_and(|prev| prev.is("{"))
+                && !token.is("}");
+            printer.newline(blank);
+        } else if let Some(prev) = prev {
+            if wants_space(prev, prev_unary, token) {
+                printer.out.push(' ');
+            }
+        }
+        printer.out.push_str(token.text);
+
+        // A trailing comment defers the break the token before it asked for
+        if token.is_comment() && token.newlines_before == 0 && pending == Break::Line {
+            prev_unary = false;
+            continue;
+        }
+        pending = Break::Default;
+        match (token.kind, token.text) {
+            (Kind::LineComment | Kind::Directive, _) => pending = Break::Line,
+            (Kind::Word, "struct") => printer.struct_next = true,
+            (Kind::Word, "case") => printer.case_label = true,
+            (Kind::Punct, "{") => {
+                printer.braces.push(printer.struct_next);
+                printer.struct_next = false;
+                printer.case_label = false;
+                if !next.is_some_and(|next| next.is("}")) {
+                    pending = Break::Line;
+                }
+            }
+            (Kind::Punct, "}") => {
+                let joined = next.is_some_and(|next| {
+                    next.text == "else" || next.is(";") || next.is(",") || next.is(")")
+                });
+                if !joined {
+                    pending = Break::Line;
+                }
+            }
+            (Kind::Punct, ";") if printer.parens == 0 => pending = Break::Line,
+            (Kind::Punct, ":") => printer.case_label = false,
+            (Kind::Punct, ",") => {
+                let in_struct = printer.braces.last() == Some(&true);
+                if in_struct && printer.parens == 0 && printer.templates == 0 {
+                    pending = Break::Line;
+                }
+            }
+            (Kind::Punct, "(" | "[") => printer.parens += 1,
+            _ => {}
+        }
+        match token.template {
+            Some(Template::Open) => printer.templates += 1,
+            Some(Template::Close) => printer.templates = printer.templates.saturating_sub(1),
+            None => {}
+        }
+        prev_unary = unary;
+    }
+
+    let mut out = printer.out;
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    if !out.is_empty() {
+        out.push('\n');
+    }
+
+    let same = |a: &[Token], b: &[Token]| {
+        a.len() == b.len()
+            && a.iter()
+                .zip(b)
+                .all(|(a, b)| a.kind == b.kind && a.text == b.text)
+    };
+    if !same(&tokens, &lex(&out)?) {
+        return Err(FormatError::TokensChanged);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> String {
+        format_wgsl(source, &FormatOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_format_layout() {
+        let source = "\
+struct VertexOutput{@builtin(position) position:vec4<f32>,@location(0) color:vec3<f32>}
+@vertex
+fn vs_main(@builtin(vertex_index) index:u32)->VertexOutput
+{
+var out:VertexOutput;let x=f32(index)*-0.5;
+if(x<0.0&&index>=2u){out.color=vec3<f32>(1.0,0.0,0.0);}
+else{out.color=vec3(0.0);}
+  for(var i=0;i<4;i++){out.position[i]=x;}
+return out;}
+";
+        let expected = "\
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>
+}
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32(index) * -0.5;
+    if (x < 0.0 && index >= 2u) {
+        out.color = vec3<f32>(1.0, 0.0, 0.0);
+    } else {
+        out.color = vec3(0.0);
+    }
+    for (var i = 0; i < 4; i++) {
+        out.position[i] = x;
+    }
+    return out;
+}
+";
+        assert_eq!(format(source), expected);
+        assert_eq!(format(expected), expected);
+        assert!(naga::front::wgsl::parse_str(expected).is_ok());
+    }
+
+    #[test]
+    fn test_comments_and_directives_are_kept() {
+        let source = "\
+#import playground::math
+
+// Leading comment
+
+
+const A:f32=1.0;   // trailing
+/* block
+   comment */
+fn f()->f32{return -A  /* inline */  ;}
+";
+        let expected = "\
+#import playground::math
+
+// Leading comment
+
+const A: f32 = 1.0; // trailing
+/* block
+   comment */
+fn f() -> f32 {
+    return -A /* inline */;
+}
+";
+        assert_eq!(format(source), expected);
+        assert_eq!(format(expected), expected);
+    }
+
+    #[test]
+    fn test_templates_and_operators() {
+        assert_eq!(
+            format("var<storage,read_write> a:array<vec2<f32>,4>;"),
+            "var<storage, read_write> a: array<vec2<f32>, 4>;\n"
+        );
+        assert_eq!(format("let b=a<<2u>>1u;"), "let b = a << 2u >> 1u;\n");
+        assert_eq!(
+            format("x=y- -z;p=&v;q=*p;"),
+            "x = y - -z;\np = &v;\nq = *p;\n"
+        );
+        assert_eq!(
+            format("let c=select(1,2,a<b);let d=1e-3+0x1p-2;"),
+            "let c = select(1, 2, a < b);\nlet d = 1e-3 + 0x1p-2;\n"
+        );
+    }
+
+    #[test]
+    fn test_line_breaks_inside_expressions_are_kept() {
+        let source = "fn f(\na: f32,\nb: f32,\n) -> f32 {\nreturn a +\nb;\n}\nfn g() {}\n";
+        let expected =
+            "fn f(\n    a: f32,\n    b: f32,\n) -> f32 {\n    return a +\n    b;\n}\nfn g() {}\n";
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_indent_width_and_switch() {
+        let source = "fn f(x:i32){switch x{case 1,2:{return;}default:{}}}";
+        let options = FormatOptions { indent_width: 2 };
+        assert_eq!(
+            format_wgsl(source, &options).unwrap(),
+            "fn f(x: i32) {\n  switch x {\n    case 1, 2: {\n      return;\n    }\n    default: {}\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_unterminated_comment() {
+        assert_eq!(
+            format_wgsl("fn f() {}\n/* open /* nested */", &FormatOptions::default()),
+            Err(FormatError::UnterminatedComment { line: 2 })
+        );
+    }
+}
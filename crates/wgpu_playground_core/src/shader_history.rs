@@ -0,0 +1,409 @@
+//! Local version history of the shaders edited in the playground
+//!
+//! Every shader (identified by its file path, or its label when it has no
+//! file) keeps a list of snapshots: automatic ones taken after each
+//! successful compile and named ones saved by hand. The history is part of
+//! the shader editor state, so it is saved with the project file. Old
+//! automatic snapshots are dropped once there are more than
+//! [`MAX_AUTOMATIC_SNAPSHOTS`]; named snapshots are only removed explicitly.
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Automatic snapshots kept per shader
+pub const MAX_AUTOMATIC_SNAPSHOTS: usize = 30;
+
+/// One saved version of a shader
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShaderSnapshot {
+    /// Identifier, unique within the shader's history
+    pub id: u64,
+    /// Name given by the user, `None` for automatic snapshots
+    pub name: Option<String>,
+    pub source: String,
+    pub created: DateTime<Utc>,
+}
+
+impl ShaderSnapshot {
+    pub fn is_automatic(&self) -> bool {
+        self.name.is_none()
+    }
+
+    /// Name of named snapshots, the local creation time of automatic ones
+    pub fn title(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("Compiled {}", self.time_label()),
+        }
+    }
+
+    /// Local creation time, e.g. "2024-03-07 09:05:02"
+    pub fn time_label(&self) -> String {
+        self.created
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+}
+
+/// Snapshots of a single shader, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Versions {
+    next_id: u64,
+    snapshots: Vec<ShaderSnapshot>,
+}
+
+impl Versions {
+    fn push(&mut self, name: Option<String>, source: &str) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.snapshots.push(ShaderSnapshot {
+            id,
+            name,
+            source: source.to_string(),
+            created: Utc::now(),
+        });
+        self.prune();
+        id
+    }
+
+    /// Drop the oldest automatic snapshots beyond the limit
+    fn prune(&mut self) {
+        let automatic = self.snapshots.iter().filter(|s| s.is_automatic()).count();
+        let mut excess = automatic.saturating_sub(MAX_AUTOMATIC_SNAPSHOTS);
+        self.snapshots.retain(|s| {
+            if excess > 0 && s.is_automatic() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// Version history of all shaders, keyed by shader
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShaderHistory {
+    shaders: BTreeMap<String, Versions>,
+}
+
+impl ShaderHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shaders.values().all(|v| v.snapshots.is_empty())
+    }
+
+    /// Snapshots of a shader, oldest first
+    pub fn snapshots(&self, shader: &str) -> &[ShaderSnapshot] {
+        self.shaders
+            .get(shader)
+            .map(|v| v.snapshots.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, shader: &str, id: u64) -> Option<&ShaderSnapshot> {
+        self.snapshots(shader).iter().find(|s| s.id == id)
+    }
+
+    /// Most recent snapshot of a shader
+    pub fn latest(&self, shader: &str) -> Option<&ShaderSnapshot> {
+        self.snapshots(shader).last()
+    }
+
+    /// Take an automatic snapshot, unless the source equals the latest one
+    ///
+    /// Returns the id of the new snapshot.
+    pub fn record(&mut self, shader: &str, source: &str) -> Option<u64> {
+        if self.latest(shader).is_some_and(|s| s.source == source) {
+            return None;
+        }
+        Some(self.versions_mut(shader).push(None, source))
+    }
+
+    /// Save a named snapshot; a blank name falls back to an automatic one
+    pub fn save(&mut self, shader: &str, name: &str, source: &str) -> u64 {
+        let name = Some(name.trim())
+            .filter(|n| !n.is_empty())
+            .map(String::from);
+        self.versions_mut(shader).push(name, source)
+    }
+
+    /// Rename a snapshot; a blank name makes it automatic again
+    pub fn rename(&mut self, shader: &str, id: u64, name: &str) -> bool {
+        let Some(snapshot) = self
+            .shaders
+            .get_mut(shader)
+            .and_then(|v| v.snapshots.iter_mut().find(|s| s.id == id))
+        else {
+            return false;
+        };
+        snapshot.name = Some(name.trim())
+            .filter(|n| !n.is_empty())
+            .map(String::from);
+        true
+    }
+
+    pub fn remove(&mut self, shader: &str, id: u64) -> bool {
+        let Some(versions) = self.shaders.get_mut(shader) else {
+            return false;
+        };
+        let len = versions.snapshots.len();
+        versions.snapshots.retain(|s| s.id != id);
+        versions.snapshots.len() != len
+    }
+
+    /// Remove all snapshots of a shader
+    pub fn clear(&mut self, shader: &str) {
+        self.shaders.remove(shader);
+    }
+
+    fn versions_mut(&mut self, shader: &str) -> &mut Versions {
+        self.shaders.entry(shader.to_string()).or_default()
+    }
+}
+
+/// How a line differs between two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Same,
+    Added,
+    Removed,
+}
+
+/// A line of a [`diff_lines`] result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine<'a> {
+    pub kind: DiffKind,
+    /// 1-based line in the old version, `None` for added lines
+    pub old_line: Option<usize>,
+    /// 1-based line in the new version, `None` for removed lines
+    pub new_line: Option<usize>,
+    pub text: &'a str,
+}
+
+/// Line diff from `old` to `new`, based on their longest common subsequence
+///
+/// Removed lines come before the added lines replacing them.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Only the middle part between a common prefix and suffix needs the table
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j]: length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let same = |lines: &mut Vec<DiffLine<'a>>, i: usize, j: usize, text| {
+        lines.push(DiffLine {
+            kind: DiffKind::Same,
+            old_line: Some(i + 1),
+            new_line: Some(j + 1),
+            text,
+        })
+    };
+    for (i, text) in old[..prefix].iter().enumerate() {
+        same(&mut lines, i, i, text);
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            same(&mut lines, prefix + i, prefix + j, a[i]);
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine {
+                kind: DiffKind::Removed,
+                old_line: Some(prefix + i + 1),
+                new_line: None,
+                text: a[i],
+            });
+            i += 1;
+        } else {
+            lines.push(DiffLine {
+                kind: DiffKind::Added,
+                old_line: None,
+                new_line: Some(prefix + j + 1),
+                text: b[j],
+            });
+            j += 1;
+        }
+    }
+    for k in 0..suffix {
+        let (oi, ni) = (prefix + a.len() + k, prefix + b.len() + k);
+        same(&mut lines, oi, ni, old[oi]);
+    }
+    lines
+}
+
+/// Number of added and removed lines of a diff
+pub fn diff_stats(diff: &[DiffLine]) -> (usize, usize) {
+    let count = |kind| diff.iter().filter(|l| l.kind == kind).count();
+    (count(DiffKind::Added), count(DiffKind::Removed))
+}
+
+/// Ranges of diff lines worth showing: changes with `context` lines around
+///
+/// Overlapping or touching ranges are merged.
+pub fn hunks(diff: &[DiffLine], context: usize) -> Vec<Range<usize>> {
+    let mut hunks: Vec<Range<usize>> = Vec::new();
+    for (index, line) in diff.iter().enumerate() {
+        if line.kind == DiffKind::Same {
+            continue;
+        }
+        let range = index.saturating_sub(context)..(index + context + 1).min(diff.len());
+        match hunks.last_mut() {
+            Some(last) if last.end >= range.start => last.end = range.end,
+            _ => hunks.push(range),
+        }
+    }
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(diff: &[DiffLine]) -> String {
+        diff.iter()
+            .map(|l| match l.kind {
+                DiffKind::Same => ' ',
+                DiffKind::Added => '+',
+                DiffKind::Removed => '-',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_record_skips_unchanged_source() {
+        let mut history = ShaderHistory::new();
+        assert!(history.is_empty());
+        assert_eq!(history.record("a.wgsl", "v1"), Some(0));
+        assert_eq!(history.record("a.wgsl", "v1"), None);
+        assert_eq!(history.record("a.wgsl", "v2"), Some(1));
+        assert_eq!(history.record("b.wgsl", "v2"), Some(0));
+
+        assert_eq!(history.snapshots("a.wgsl").len(), 2);
+        assert_eq!(history.latest("a.wgsl").unwrap().source, "v2");
+        assert!(history.snapshots("c.wgsl").is_empty());
+        assert!(history.latest("a.wgsl").unwrap().is_automatic());
+    }
+
+    #[test]
+    fn test_named_snapshots_survive_pruning() {
+        let mut history = ShaderHistory::new();
+        let named = history.save("a.wgsl", "  Before lighting ", "v0");
+        assert_eq!(history.save("a.wgsl", " ", "v0"), named + 1);
+        for i in 0..MAX_AUTOMATIC_SNAPSHOTS + 5 {
+            history.record("a.wgsl", &format!("v{}", i + 1));
+        }
+
+        let snapshots = history.snapshots("a.wgsl");
+        assert_eq!(snapshots.len(), MAX_AUTOMATIC_SNAPSHOTS + 1);
+        assert_eq!(snapshots[0].title(), "Before lighting");
+        assert_eq!(snapshots.last().unwrap().source, "v35");
+        assert_eq!(
+            snapshots.iter().filter(|s| s.is_automatic()).count(),
+            MAX_AUTOMATIC_SNAPSHOTS
+        );
+        // Ids keep counting after pruning
+        assert_eq!(
+            snapshots.last().unwrap().id,
+            MAX_AUTOMATIC_SNAPSHOTS as u64 + 6
+        );
+    }
+
+    #[test]
+    fn test_rename_and_remove() {
+        let mut history = ShaderHistory::new();
+        let id = history.record("a.wgsl", "v1").unwrap();
+        assert!(history.rename("a.wgsl", id, "Working"));
+        assert_eq!(history.get("a.wgsl", id).unwrap().title(), "Working");
+        assert!(!history.rename("a.wgsl", id + 1, "Missing"));
+
+        assert!(history.remove("a.wgsl", id));
+        assert!(!history.remove("a.wgsl", id));
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_history_round_trips_through_json() {
+        let mut history = ShaderHistory::new();
+        history.save("a.wgsl", "First", "fn main() {}\n");
+        history.record("shader_editor", "fn other() {}\n");
+
+        let json = serde_json::to_string(&history).unwrap();
+        let loaded: ShaderHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, history);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "a\nb\nc\nd\ne";
+        let new = "a\nc\nx\nd\ne\nf";
+        let diff = diff_lines(old, new);
+        assert_eq!(kinds(&diff), " - +  +");
+        assert_eq!(diff_stats(&diff), (2, 1));
+
+        let removed = &diff[1];
+        assert_eq!(
+            (removed.text, removed.old_line, removed.new_line),
+            ("b", Some(2), None)
+        );
+        let added = &diff[3];
+        assert_eq!(
+            (added.text, added.old_line, added.new_line),
+            ("x", None, Some(3))
+        );
+        let last = diff.last().unwrap();
+        assert_eq!((last.text, last.new_line), ("f", Some(6)));
+
+        // Replaced lines list the removal first
+        assert_eq!(kinds(&diff_lines("a\nb\nc", "a\nB\nc")), " -+ ");
+        assert!(diff_lines(old, old)
+            .iter()
+            .all(|l| l.kind == DiffKind::Same));
+        assert_eq!(kinds(&diff_lines("", "a\nb")), "++");
+    }
+
+    #[test]
+    fn test_hunks() {
+        let old: String = (0..20).map(|i| format!("{}\n", i)).collect();
+        let new: String = (0..20)
+            .filter(|&i| i != 17)
+            .map(|i| match i {
+                3 => "three\n".to_string(),
+                5 => "five\n".to_string(),
+                _ => format!("{}\n", i),
+            })
+            .collect();
+        let diff = diff_lines(&old, &new);
+        // 3 and 5 are close enough to share a hunk, 17 gets its own
+        assert_eq!(hunks(&diff, 2), vec![1..10, 17..22]);
+        assert!(hunks(&diff_lines(&old, &old), 2).is_empty());
+    }
+}
@@ -0,0 +1,619 @@
+//! Textual `#include` for WGSL
+//!
+//! Where [`shader_composer`](crate::shader_composer) imports namespaced
+//! modules, this pastes whole files into a shader, like the C
+//! preprocessor:
+//!
+//! ```wgsl
+//! #include "post_processing_common.wgsl"
+//!
+//! @fragment
+//! fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> { ... }
+//! ```
+//!
+//! `#include "file"` looks next to the including file first and then in
+//! the include paths; `#include <file>` only looks in the include paths.
+//! Each file is pasted once per shader, so including a file twice (say,
+//! through two other includes) is harmless, but a file that includes
+//! itself, directly or not, is an error.
+//!
+//! Expansion changes line numbers, so [`ExpandedShader`] keeps the file and
+//! line every output line came from, and [`ExpandedShader::parse`] reports
+//! naga errors against the original file.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Name of the shader being expanded when it isn't a file
+pub const ROOT_NAME: &str = "shader";
+
+/// Errors that can occur while expanding includes
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncludeError {
+    /// An `#include` line could not be parsed
+    MalformedDirective {
+        file: String,
+        line: usize,
+        message: String,
+    },
+    /// No include path has the file
+    NotFound {
+        file: String,
+        line: usize,
+        include: String,
+    },
+    /// The file exists but could not be read
+    Unreadable {
+        file: String,
+        line: usize,
+        include: String,
+        message: String,
+    },
+    /// Files include each other; lists the cycle, first file repeated
+    IncludeCycle(Vec<String>),
+}
+
+impl fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IncludeError::MalformedDirective {
+                file,
+                line,
+                message,
+            } => write!(f, "{}, line {}: {}", file, line, message),
+            IncludeError::NotFound {
+                file,
+                line,
+                include,
+            } => write!(f, "{}, line {}: cannot find '{}'", file, line, include),
+            IncludeError::Unreadable {
+                file,
+                line,
+                include,
+                message,
+            } => write!(
+                f,
+                "{}, line {}: cannot read '{}': {}",
+                file, line, include, message
+            ),
+            IncludeError::IncludeCycle(cycle) => {
+                write!(f, "Include cycle: {}", cycle.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for IncludeError {}
+
+impl IncludeError {
+    /// File and line of the directive that failed
+    pub fn location(&self) -> Option<(&str, usize)> {
+        match self {
+            IncludeError::MalformedDirective { file, line, .. }
+            | IncludeError::NotFound { file, line, .. }
+            | IncludeError::Unreadable { file, line, .. } => Some((file, *line)),
+            IncludeError::IncludeCycle(_) => None,
+        }
+    }
+}
+
+/// Where a line of an expanded shader came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineOrigin<'a> {
+    /// File as named in its `#include`, or the shader's own name
+    pub file: &'a str,
+    /// Line within `file` (1-indexed)
+    pub line: usize,
+    /// Line of the shader itself that pulled the line in: the line itself
+    /// or the `#include` it came through
+    pub root_line: usize,
+    /// Whether the line came from an included file
+    pub included: bool,
+}
+
+/// A parse error located in the file it occurred in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+    pub message: String,
+    pub file: String,
+    /// 1-indexed; `None` when naga gave no location
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Line of the shader itself the error is reached through
+    pub root_line: Option<usize>,
+}
+
+impl fmt::Display for ShaderDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "{}:{}:{}: {}", self.file, line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "{}:{}: {}", self.file, line, self.message),
+            _ => write!(f, "{}: {}", self.file, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ShaderDiagnostic {}
+
+#[derive(Debug, Clone, Copy)]
+struct Origin {
+    /// Index into `ExpandedShader::files`
+    file: usize,
+    line: usize,
+    root_line: usize,
+}
+
+/// A shader with its includes pasted in
+#[derive(Debug, Clone)]
+pub struct ExpandedShader {
+    /// Plain WGSL
+    pub source: String,
+    /// The shader's name first, then each included file in the order it
+    /// was pasted
+    pub files: Vec<String>,
+    /// Origin of every line of `source`
+    lines: Vec<Origin>,
+}
+
+impl ExpandedShader {
+    /// Map a line of the expanded source (1-indexed) back to its file
+    pub fn locate(&self, line: usize) -> Option<LineOrigin<'_>> {
+        let origin = self.lines.get(line.checked_sub(1)?)?;
+        Some(LineOrigin {
+            file: &self.files[origin.file],
+            line: origin.line,
+            root_line: origin.root_line,
+            included: origin.file != 0,
+        })
+    }
+
+    /// Whether any file was pasted in
+    pub fn has_includes(&self) -> bool {
+        self.files.len() > 1
+    }
+
+    /// Parse the expanded source with naga
+    ///
+    /// Errors point at the file and line the offending code was written
+    /// in rather than at the expanded source.
+    pub fn parse(&self) -> Result<naga::Module, ShaderDiagnostic> {
+        naga::front::wgsl::parse_str(&self.source).map_err(|e| {
+            let location = e.location(&self.source);
+            let origin = location.and_then(|location| self.locate(location.line_number as usize));
+            ShaderDiagnostic {
+                message: e.message().to_string(),
+                file: origin
+                    .map_or(self.files[0].as_str(), |origin| origin.file)
+                    .to_string(),
+                line: origin.map(|origin| origin.line),
+                column: origin
+                    .and(location)
+                    .map(|location| location.line_position as usize),
+                root_line: origin.map(|origin| origin.root_line),
+            }
+        })
+    }
+}
+
+/// A found include, before it is read
+struct Found {
+    /// Identifies the file across spellings of its name
+    key: String,
+    path: Option<PathBuf>,
+    /// In-memory contents
+    source: Option<String>,
+}
+
+/// Finds included files and expands shaders
+#[derive(Debug, Clone, Default)]
+pub struct IncludeResolver {
+    include_paths: Vec<PathBuf>,
+    /// In-memory files, found before anything on disk
+    files: BTreeMap<String, String>,
+}
+
+impl IncludeResolver {
+    /// A resolver with no include paths
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A resolver searching the user shaders directory and then the
+    /// bundled one, so user files override bundled ones
+    pub fn with_shader_dirs() -> Self {
+        let mut resolver = Self::new();
+        if let Some(user_dir) = crate::assets::user_assets_dir() {
+            resolver.add_include_path(user_dir.join(crate::assets::AssetKind::Shader.subdir()));
+        }
+        resolver.add_include_path(
+            crate::assets::builtin_assets_dir().join(crate::assets::AssetKind::Shader.subdir()),
+        );
+        resolver
+    }
+
+    /// Search `path` after the include paths added before it
+    pub fn add_include_path(&mut self, path: impl Into<PathBuf>) {
+        self.include_paths.push(path.into());
+    }
+
+    pub fn include_paths(&self) -> &[PathBuf] {
+        &self.include_paths
+    }
+
+    /// Provide a file from memory, e.g. on the web or in tests
+    pub fn add_file(&mut self, name: impl Into<String>, source: impl Into<String>) {
+        self.files.insert(name.into(), source.into());
+    }
+
+    /// Expand the includes of `source`
+    ///
+    /// `path` is where `source` was read from, if anywhere: quoted
+    /// includes are looked up next to it, and it names the shader in
+    /// errors and line origins.
+    pub fn expand(
+        &self,
+        source: &str,
+        path: Option<&Path>,
+    ) -> Result<ExpandedShader, IncludeError> {
+        let name = shader_name(path);
+        let mut expansion = Expansion {
+            resolver: self,
+            shader: ExpandedShader {
+                source: String::with_capacity(source.len()),
+                files: vec![name.clone()],
+                lines: Vec::new(),
+            },
+            included: HashSet::new(),
+            stack: Vec::new(),
+        };
+        if let Some(path) = path {
+            let key = file_key(path);
+            expansion.included.insert(key.clone());
+            expansion.stack.push((key, name));
+        }
+        let dir = path.and_then(Path::parent).map(Path::to_path_buf);
+        expansion.paste(0, source, dir.as_deref(), None)?;
+        Ok(expansion.shader)
+    }
+
+    fn find(&self, include: &Include, dir: Option<&Path>) -> Option<Found> {
+        if let Some(source) = self.files.get(&include.name) {
+            return Some(Found {
+                key: format!("memory:{}", include.name),
+                path: None,
+                source: Some(source.clone()),
+            });
+        }
+        let local = dir.filter(|_| !include.angled);
+        local
+            .into_iter()
+            .chain(self.include_paths.iter().map(PathBuf::as_path))
+            .map(|dir| dir.join(&include.name))
+            .find(|path| path.is_file())
+            .map(|path| Found {
+                key: file_key(&path),
+                path: Some(path),
+                source: None,
+            })
+    }
+}
+
+/// One `#include` directive
+#[derive(Debug, Clone, PartialEq)]
+struct Include {
+    name: String,
+    /// Written `<name>` rather than `"name"`
+    angled: bool,
+}
+
+struct Expansion<'a> {
+    resolver: &'a IncludeResolver,
+    shader: ExpandedShader,
+    /// Keys of the files pasted so far
+    included: HashSet<String>,
+    /// Keys and names of the files being pasted, outermost first
+    stack: Vec<(String, String)>,
+}
+
+impl Expansion<'_> {
+    /// Paste `source`, file `file` of the shader, expanding its includes
+    ///
+    /// `root_line` is the line of the shader's own `#include` the file is
+    /// reached through, `None` for the shader itself.
+    fn paste(
+        &mut self,
+        file: usize,
+        source: &str,
+        dir: Option<&Path>,
+        root_line: Option<usize>,
+    ) -> Result<(), IncludeError> {
+        for (index, text) in source.lines().enumerate() {
+            let line = index + 1;
+            let root_line = root_line.unwrap_or(line);
+            let include = match parse_directive(text) {
+                None => {
+                    self.push_line(text, file, line, root_line);
+                    continue;
+                }
+                Some(Ok(include)) => include,
+                Some(Err(message)) => {
+                    return Err(IncludeError::MalformedDirective {
+                        file: self.shader.files[file].clone(),
+                        line,
+                        message,
+                    })
+                }
+            };
+
+            let found =
+                self.resolver
+                    .find(&include, dir)
+                    .ok_or_else(|| IncludeError::NotFound {
+                        file: self.shader.files[file].clone(),
+                        line,
+                        include: include.name.clone(),
+                    })?;
+            if let Some(start) = self.stack.iter().position(|(key, _)| *key == found.key) {
+                let mut cycle: Vec<String> = self.stack[start..]
+                    .iter()
+                    .map(|(_, name)| name.clone())
+                    .collect();
+                cycle.push(include.name);
+                return Err(IncludeError::IncludeCycle(cycle));
+            }
+            // Already pasted; keep the directive's line so lines still map
+            if !self.included.insert(found.key.clone()) {
+                self.push_line("", file, line, root_line);
+                continue;
+            }
+
+            let included = match (found.source, &found.path) {
+                (Some(source), _) => source,
+                (None, Some(path)) => crate::assets::load_string_from_path(path).map_err(|e| {
+                    IncludeError::Unreadable {
+                        file: self.shader.files[file].clone(),
+                        line,
+                        include: include.name.clone(),
+                        message: e.to_string(),
+                    }
+                })?,
+                (None, None) => unreachable!("includes are found in memory or on disk"),
+            };
+            self.shader.files.push(include.name.clone());
+            let included_file = self.shader.files.len() - 1;
+            self.stack.push((found.key, include.name));
+            let included_dir = found.path.as_deref().and_then(Path::parent);
+            self.paste(included_file, &included, included_dir, Some(root_line))?;
+            self.stack.pop();
+        }
+        Ok(())
+    }
+
+    fn push_line(&mut self, text: &str, file: usize, line: usize, root_line: usize) {
+        self.shader.source.push_str(text);
+        self.shader.source.push('\n');
+        self.shader.lines.push(Origin {
+            file,
+            line,
+            root_line,
+        });
+    }
+}
+
+/// Name errors and line origins give a shader read from `path`
+pub fn shader_name(path: Option<&Path>) -> String {
+    path.and_then(Path::file_name)
+        .map_or(ROOT_NAME.to_string(), |name| {
+            name.to_string_lossy().into_owned()
+        })
+}
+
+/// Same file whichever relative path reached it
+fn file_key(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// Parse an `#include` line; `None` for any other line
+fn parse_directive(text: &str) -> Option<Result<Include, String>> {
+    let rest = text.trim_start().strip_prefix("#include")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let argument = rest.trim();
+    let (angled, close) = match argument.chars().next() {
+        Some('"') => (false, '"'),
+        Some('<') => (true, '>'),
+        _ => {
+            return Some(Err(
+                "expected a file name in quotes or angle brackets after #include".to_string(),
+            ))
+        }
+    };
+    let name = match argument[1..].find(close) {
+        Some(end) if argument[end + 2..].trim().is_empty() => &argument[1..end + 1],
+        Some(_) => return Some(Err("unexpected text after the file name".to_string())),
+        None => return Some(Err(format!("missing closing {}", close))),
+    };
+    if name.trim().is_empty() {
+        return Some(Err("empty file name".to_string()));
+    }
+    Some(Ok(Include {
+        name: name.to_string(),
+        angled,
+    }))
+}
+
+/// Whether a source uses `#include`
+pub fn has_includes(source: &str) -> bool {
+    source
+        .lines()
+        .any(|line| matches!(parse_directive(line), Some(Ok(_))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMON: &str = "\
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+}
+";
+
+    fn resolver() -> IncludeResolver {
+        let mut resolver = IncludeResolver::new();
+        resolver.add_file("common.wgsl", COMMON);
+        resolver.add_file(
+            "shade.wgsl",
+            "#include \"common.wgsl\"\nfn shade() -> f32 {\n    return 1.0;\n}\n",
+        );
+        resolver
+    }
+
+    #[test]
+    fn test_parse_directive() {
+        assert_eq!(parse_directive("let x = 1;"), None);
+        assert_eq!(parse_directive("#includes"), None);
+        assert_eq!(
+            parse_directive("  #include \"a/b.wgsl\"  "),
+            Some(Ok(Include {
+                name: "a/b.wgsl".to_string(),
+                angled: false,
+            }))
+        );
+        assert_eq!(
+            parse_directive("#include <b.wgsl>"),
+            Some(Ok(Include {
+                name: "b.wgsl".to_string(),
+                angled: true,
+            }))
+        );
+        assert!(matches!(parse_directive("#include b.wgsl"), Some(Err(_))));
+        assert!(matches!(parse_directive("#include \"b.wgsl"), Some(Err(_))));
+        assert!(matches!(parse_directive("#include \"b\" x"), Some(Err(_))));
+        assert!(matches!(parse_directive("#include \"\""), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_expand_maps_lines_back() {
+        let source = "#include \"shade.wgsl\"\n#include \"common.wgsl\"\nfn main() {}\n";
+        let expanded = resolver().expand(source, None).unwrap();
+
+        assert_eq!(expanded.files, ["shader", "shade.wgsl", "common.wgsl"]);
+        // common.wgsl is pasted once, inside shade.wgsl
+        assert_eq!(expanded.source.matches("struct VertexOutput").count(), 1);
+
+        let origin = expanded.locate(2).unwrap();
+        assert_eq!(
+            (origin.file, origin.line, origin.root_line),
+            ("common.wgsl", 2, 1)
+        );
+        assert!(origin.included);
+        let origin = expanded.locate(4).unwrap();
+        assert_eq!(
+            (origin.file, origin.line, origin.root_line),
+            ("shade.wgsl", 2, 1)
+        );
+        // The second include left an empty line behind
+        let origin = expanded.locate(7).unwrap();
+        assert_eq!(
+            (origin.file, origin.line, origin.root_line),
+            ("shader", 2, 2)
+        );
+        let origin = expanded.locate(8).unwrap();
+        assert_eq!((origin.file, origin.line), ("shader", 3));
+        assert!(!origin.included);
+        assert_eq!(expanded.locate(9), None);
+    }
+
+    #[test]
+    fn test_parse_error_points_at_included_file() {
+        let mut resolver = resolver();
+        resolver.add_file("broken.wgsl", "fn broken() -> f32 {\n    return 1.0\n}\n");
+        let source = "#include \"common.wgsl\"\n#include \"broken.wgsl\"\n";
+        let error = resolver.expand(source, None).unwrap().parse().unwrap_err();
+
+        assert_eq!(error.file, "broken.wgsl");
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.root_line, Some(2));
+        assert!(error.to_string().starts_with("broken.wgsl:3:"));
+
+        let valid = resolver.expand("#include <common.wgsl>\n", None).unwrap();
+        assert!(valid.parse().is_ok());
+    }
+
+    #[test]
+    fn test_include_errors() {
+        let mut resolver = resolver();
+        resolver.add_file("a.wgsl", "#include \"b.wgsl\"\n");
+        resolver.add_file("b.wgsl", "// b\n#include \"a.wgsl\"\n");
+
+        assert_eq!(
+            resolver.expand("#include \"a.wgsl\"\n", None).unwrap_err(),
+            IncludeError::IncludeCycle(vec![
+                "a.wgsl".to_string(),
+                "b.wgsl".to_string(),
+                "a.wgsl".to_string(),
+            ])
+        );
+        let missing = resolver
+            .expand("fn f() {}\n#include \"missing.wgsl\"\n", None)
+            .unwrap_err();
+        assert_eq!(missing.location(), Some(("shader", 2)));
+        assert!(matches!(missing, IncludeError::NotFound { .. }));
+        assert!(matches!(
+            resolver.expand("#include missing\n", None),
+            Err(IncludeError::MalformedDirective { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_include_paths_and_relative_includes() {
+        let root = std::env::temp_dir().join(format!("shader_include_{}", std::process::id()));
+        let shaders = root.join("shaders");
+        let library = root.join("library");
+        std::fs::create_dir_all(shaders.join("nested")).unwrap();
+        std::fs::create_dir_all(&library).unwrap();
+        std::fs::write(library.join("common.wgsl"), COMMON).unwrap();
+        std::fs::write(
+            shaders.join("nested/helper.wgsl"),
+            "#include <common.wgsl>\nfn helper() {}\n",
+        )
+        .unwrap();
+        let main = shaders.join("main.wgsl");
+        std::fs::write(
+            &main,
+            "#include \"nested/helper.wgsl\"\n#include \"main.wgsl\"\n",
+        )
+        .unwrap();
+
+        let mut resolver = IncludeResolver::new();
+        resolver.add_include_path(&library);
+        let source = std::fs::read_to_string(&main).unwrap();
+        // The shader includes itself
+        assert_eq!(
+            resolver.expand(&source, Some(&main)).unwrap_err(),
+            IncludeError::IncludeCycle(vec!["main.wgsl".to_string(), "main.wgsl".to_string()])
+        );
+
+        let source = "#include \"nested/helper.wgsl\"\n";
+        let expanded = resolver.expand(source, Some(&main)).unwrap();
+        assert_eq!(
+            expanded.files,
+            ["main.wgsl", "nested/helper.wgsl", "common.wgsl"]
+        );
+        assert!(expanded.source.ends_with("fn helper() {}\n"));
+
+        // Angled includes skip the including file's directory
+        let angled = resolver.expand("#include <nested/helper.wgsl>\n", Some(&main));
+        assert!(matches!(angled, Err(IncludeError::NotFound { .. })));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
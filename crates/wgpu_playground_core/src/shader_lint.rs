@@ -0,0 +1,594 @@
+//! WGSL compatibility linter
+//!
+//! Checks a WGSL module against a target environment before any pipeline is
+//! created. A target is either the WebGPU core defaults, WebGPU compatibility
+//! mode, or a [`VirtualDevice`] loaded from a capability snapshot.
+//!
+//! The linter reports problems that naga's own validation accepts but the
+//! target would reject at pipeline creation time: built-ins that need an
+//! optional feature, too many bindings per stage, oversized workgroups and
+//! similar.
+
+use crate::capability_snapshot::VirtualDevice;
+use naga::valid::{Capabilities, ModuleInfo, ValidationFlags, Validator};
+use naga::{AddressSpace, Binding, BuiltIn, Handle, ImageClass, Module, ShaderStage, TypeInner};
+use std::fmt;
+use wgpu::{Features, Limits};
+
+/// Environment a shader is linted against
+#[derive(Debug, Clone, Default)]
+pub enum TargetProfile {
+    /// WebGPU core: default limits, no optional features
+    #[default]
+    WebGpuCore,
+    /// WebGPU compatibility mode: [`compatibility_limits`] and the compat
+    /// restrictions
+    Compatibility,
+    /// Limits and features of a captured device
+    Device(VirtualDevice),
+}
+
+impl TargetProfile {
+    /// Display name of the profile
+    pub fn name(&self) -> String {
+        match self {
+            Self::WebGpuCore => "WebGPU Core".to_string(),
+            Self::Compatibility => "WebGPU Compatibility".to_string(),
+            Self::Device(device) => format!("Snapshot: {}", device.snapshot().adapter_name),
+        }
+    }
+
+    /// Limits enforced by the profile
+    pub fn limits(&self) -> Limits {
+        match self {
+            Self::WebGpuCore => Limits::default(),
+            Self::Compatibility => compatibility_limits(),
+            Self::Device(device) => device.limits().clone(),
+        }
+    }
+
+    /// Optional features available in the profile
+    pub fn features(&self) -> Features {
+        match self {
+            Self::WebGpuCore | Self::Compatibility => Features::empty(),
+            Self::Device(device) => device.features(),
+        }
+    }
+
+    fn is_compatibility(&self) -> bool {
+        matches!(self, Self::Compatibility)
+    }
+}
+
+/// Limits of WebGPU compatibility mode
+///
+/// These are the core defaults with the values the compatibility mode
+/// proposal lowers. wgpu has no compat limit set of its own, and its
+/// downlevel defaults describe GLES 3.1 rather than WebGPU compat.
+pub fn compatibility_limits() -> Limits {
+    Limits {
+        max_texture_dimension_1d: 4096,
+        max_texture_dimension_2d: 4096,
+        max_storage_buffers_per_shader_stage: 4,
+        max_uniform_buffer_binding_size: 16384,
+        max_inter_stage_shader_variables: 15,
+        max_color_attachments: 4,
+        max_compute_invocations_per_workgroup: 128,
+        max_compute_workgroup_size_x: 128,
+        max_compute_workgroup_size_y: 128,
+        ..Limits::default()
+    }
+}
+
+/// Severity of a lint diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintSeverity {
+    /// The shader will fail on the target
+    Error,
+    /// The shader may behave differently or be slow on the target
+    Warning,
+}
+
+/// A single problem found by the linter
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintDiagnostic {
+    /// How serious the problem is
+    pub severity: LintSeverity,
+    /// Entry point the problem belongs to, if any
+    pub entry_point: Option<String>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            LintSeverity::Error => "error",
+            LintSeverity::Warning => "warning",
+        };
+        match &self.entry_point {
+            Some(entry) => write!(f, "{} [{}]: {}", severity, entry, self.message),
+            None => write!(f, "{}: {}", severity, self.message),
+        }
+    }
+}
+
+/// Per-stage resource counts of an entry point
+#[derive(Debug, Default)]
+struct ResourceCounts {
+    uniform_buffers: u32,
+    storage_buffers: u32,
+    samplers: u32,
+    sampled_textures: u32,
+    storage_textures: u32,
+}
+
+/// Lint WGSL source against a target profile
+///
+/// Returns an error message if the source does not parse or validate at all;
+/// otherwise returns the (possibly empty) list of diagnostics.
+pub fn lint_wgsl(source: &str, profile: &TargetProfile) -> Result<Vec<LintDiagnostic>, String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|e| e.emit_to_string(source))?;
+    Ok(lint_module(&module, &info, profile))
+}
+
+/// Lint an already validated naga module against a target profile
+pub fn lint_module(
+    module: &Module,
+    info: &ModuleInfo,
+    profile: &TargetProfile,
+) -> Vec<LintDiagnostic> {
+    let mut linter = Linter {
+        module,
+        features: profile.features(),
+        limits: profile.limits(),
+        compat: profile.is_compatibility(),
+        diagnostics: Vec::new(),
+    };
+    linter.check_module();
+    for (index, entry) in module.entry_points.iter().enumerate() {
+        linter.check_entry_point(entry, info, index);
+    }
+    linter.diagnostics
+}
+
+struct Linter<'a> {
+    module: &'a Module,
+    features: Features,
+    limits: Limits,
+    compat: bool,
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl Linter<'_> {
+    fn push(&mut self, severity: LintSeverity, entry: Option<&str>, message: String) {
+        self.diagnostics.push(LintDiagnostic {
+            severity,
+            entry_point: entry.map(str::to_string),
+            message,
+        });
+    }
+
+    fn require(&mut self, feature: Features, entry: Option<&str>, what: &str) {
+        if !self.features.contains(feature) {
+            let name = feature
+                .iter_names()
+                .next()
+                .map(|(name, _)| name)
+                .unwrap_or("unknown");
+            self.push(
+                LintSeverity::Error,
+                entry,
+                format!("{} requires the {} feature", what, name),
+            );
+        }
+    }
+
+    fn check_module(&mut self) {
+        let uses_f16 = self.module.types.iter().any(|(_, ty)| {
+            let scalar = match ty.inner {
+                TypeInner::Scalar(scalar)
+                | TypeInner::Vector { scalar, .. }
+                | TypeInner::Matrix { scalar, .. } => scalar,
+                _ => return false,
+            };
+            scalar.kind == naga::ScalarKind::Float && scalar.width == 2
+        });
+        if uses_f16 {
+            self.require(Features::SHADER_F16, None, "f16 types");
+        }
+
+        let module = self.module;
+        for (_, global) in module.global_variables.iter() {
+            let name = global.name.as_deref().unwrap_or("<unnamed>");
+            if global.space == AddressSpace::Immediate {
+                self.require(
+                    Features::IMMEDIATES,
+                    None,
+                    &format!("Immediate data '{}'", name),
+                );
+            }
+            if let Some(binding) = &global.binding {
+                if binding.group >= self.limits.max_bind_groups {
+                    self.push(
+                        LintSeverity::Error,
+                        None,
+                        format!(
+                            "'{}' uses @group({}) but the target allows {} bind groups",
+                            name, binding.group, self.limits.max_bind_groups
+                        ),
+                    );
+                }
+                if binding.binding >= self.limits.max_bindings_per_bind_group {
+                    self.push(
+                        LintSeverity::Error,
+                        None,
+                        format!(
+                            "'{}' uses @binding({}) but the target allows {} bindings per group",
+                            name, binding.binding, self.limits.max_bindings_per_bind_group
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_entry_point(&mut self, entry: &naga::EntryPoint, info: &ModuleInfo, index: usize) {
+        let name = entry.name.as_str();
+        let module = self.module;
+
+        for argument in &entry.function.arguments {
+            self.check_binding_tree(argument.binding.as_ref(), argument.ty, name);
+        }
+        if let Some(result) = &entry.function.result {
+            self.check_binding_tree(result.binding.as_ref(), result.ty, name);
+        }
+
+        if entry.stage == ShaderStage::Compute {
+            self.check_workgroup_size(entry.workgroup_size, name);
+        }
+
+        let function_info = info.get_entry_point(index);
+        let mut counts = ResourceCounts::default();
+        let mut workgroup_bytes = 0u32;
+        for (handle, global) in module.global_variables.iter() {
+            if function_info[handle].is_empty() {
+                continue;
+            }
+            match global.space {
+                AddressSpace::Uniform => counts.uniform_buffers += 1,
+                AddressSpace::Storage { .. } => counts.storage_buffers += 1,
+                AddressSpace::Handle => match self.resource_type(global.ty) {
+                    TypeInner::Sampler { .. } => counts.samplers += 1,
+                    TypeInner::Image {
+                        class: ImageClass::Storage { .. },
+                        ..
+                    } => counts.storage_textures += 1,
+                    TypeInner::Image { .. } => counts.sampled_textures += 1,
+                    _ => {}
+                },
+                AddressSpace::WorkGroup => {
+                    workgroup_bytes += module.types[global.ty].inner.size(module.to_ctx());
+                }
+                _ => {}
+            }
+        }
+
+        if workgroup_bytes > self.limits.max_compute_workgroup_storage_size {
+            self.push(
+                LintSeverity::Error,
+                Some(name),
+                format!(
+                    "uses {} bytes of workgroup storage, limit is {}",
+                    workgroup_bytes, self.limits.max_compute_workgroup_storage_size
+                ),
+            );
+        }
+        self.check_resource_counts(&counts, entry.stage, name);
+    }
+
+    /// Type of a resource, looking through binding arrays
+    fn resource_type(&self, ty: Handle<naga::Type>) -> &TypeInner {
+        match &self.module.types[ty].inner {
+            TypeInner::BindingArray { base, .. } => &self.module.types[*base].inner,
+            inner => inner,
+        }
+    }
+
+    fn check_binding_tree(
+        &mut self,
+        binding: Option<&Binding>,
+        ty: Handle<naga::Type>,
+        entry: &str,
+    ) {
+        match binding {
+            Some(binding) => self.check_binding(binding, entry),
+            None => {
+                let module = self.module;
+                if let TypeInner::Struct { members, .. } = &module.types[ty].inner {
+                    for member in members {
+                        if let Some(binding) = &member.binding {
+                            self.check_binding(binding, entry);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_binding(&mut self, binding: &Binding, entry: &str) {
+        let entry = Some(entry);
+        match binding {
+            Binding::BuiltIn(builtin) => match builtin {
+                BuiltIn::PrimitiveIndex => {
+                    self.require(Features::PRIMITIVE_INDEX, entry, "@builtin(primitive_index)")
+                }
+                BuiltIn::ViewIndex => {
+                    self.require(Features::MULTIVIEW, entry, "@builtin(view_index)")
+                }
+                BuiltIn::Barycentric { .. } => {
+                    self.require(Features::SHADER_BARYCENTRICS, entry, "@builtin(barycentric)")
+                }
+                BuiltIn::ClipDistances => {
+                    self.require(Features::CLIP_DISTANCES, entry, "@builtin(clip_distances)")
+                }
+                BuiltIn::NumSubgroups
+                | BuiltIn::SubgroupId
+                | BuiltIn::SubgroupSize
+                | BuiltIn::SubgroupInvocationId => {
+                    self.require(Features::SUBGROUP, entry, "Subgroup built-ins")
+                }
+                BuiltIn::SampleIndex | BuiltIn::SampleMask if self.compat => self.push(
+                    LintSeverity::Error,
+                    entry,
+                    "@builtin(sample_index) and @builtin(sample_mask) are not available in compatibility mode".to_string(),
+                ),
+                _ => {}
+            },
+            Binding::Location {
+                location,
+                interpolation,
+                sampling,
+                ..
+            } => {
+                if self.compat {
+                    if *interpolation == Some(naga::Interpolation::Linear) {
+                        self.push(
+                            LintSeverity::Error,
+                            entry,
+                            format!(
+                                "@location({}) uses linear interpolation, which compatibility mode does not support",
+                                location
+                            ),
+                        );
+                    }
+                    if *sampling == Some(naga::Sampling::Sample) {
+                        self.push(
+                            LintSeverity::Error,
+                            entry,
+                            format!(
+                                "@location({}) uses per-sample interpolation, which compatibility mode does not support",
+                                location
+                            ),
+                        );
+                    }
+                }
+                if *location >= self.limits.max_inter_stage_shader_variables {
+                    self.push(
+                        LintSeverity::Error,
+                        entry,
+                        format!(
+                            "@location({}) exceeds the {} inter-stage variables of the target",
+                            location, self.limits.max_inter_stage_shader_variables
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_workgroup_size(&mut self, size: [u32; 3], entry: &str) {
+        let axis_limits = [
+            ("x", self.limits.max_compute_workgroup_size_x),
+            ("y", self.limits.max_compute_workgroup_size_y),
+            ("z", self.limits.max_compute_workgroup_size_z),
+        ];
+        for ((axis, limit), value) in axis_limits.into_iter().zip(size) {
+            if value > limit {
+                self.push(
+                    LintSeverity::Error,
+                    Some(entry),
+                    format!(
+                        "workgroup size {} = {} exceeds the limit of {}",
+                        axis, value, limit
+                    ),
+                );
+            }
+        }
+
+        let invocations = size.iter().map(|&s| u64::from(s)).product::<u64>();
+        let max_invocations = u64::from(self.limits.max_compute_invocations_per_workgroup);
+        if invocations > max_invocations {
+            self.push(
+                LintSeverity::Error,
+                Some(entry),
+                format!(
+                    "workgroup has {} invocations, limit is {}",
+                    invocations, max_invocations
+                ),
+            );
+        } else if invocations % 32 != 0 && invocations > 1 {
+            self.push(
+                LintSeverity::Warning,
+                Some(entry),
+                format!(
+                    "workgroup has {} invocations, not a multiple of 32; some lanes may idle",
+                    invocations
+                ),
+            );
+        }
+    }
+
+    fn check_resource_counts(&mut self, counts: &ResourceCounts, stage: ShaderStage, entry: &str) {
+        let limits = &self.limits;
+        let checks = [
+            (
+                "uniform buffers",
+                counts.uniform_buffers,
+                limits.max_uniform_buffers_per_shader_stage,
+            ),
+            (
+                "storage buffers",
+                counts.storage_buffers,
+                limits.max_storage_buffers_per_shader_stage,
+            ),
+            (
+                "samplers",
+                counts.samplers,
+                limits.max_samplers_per_shader_stage,
+            ),
+            (
+                "sampled textures",
+                counts.sampled_textures,
+                limits.max_sampled_textures_per_shader_stage,
+            ),
+            (
+                "storage textures",
+                counts.storage_textures,
+                limits.max_storage_textures_per_shader_stage,
+            ),
+        ];
+        for (what, count, limit) in checks {
+            if count > limit {
+                self.push(
+                    LintSeverity::Error,
+                    Some(entry),
+                    format!("uses {} {}, limit per stage is {}", count, what, limit),
+                );
+            }
+        }
+
+        if self.compat
+            && stage == ShaderStage::Vertex
+            && (counts.storage_buffers > 0 || counts.storage_textures > 0)
+        {
+            self.push(
+                LintSeverity::Error,
+                Some(entry),
+                "vertex shaders cannot access storage buffers or storage textures in compatibility mode"
+                    .to_string(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(source: &str, profile: &TargetProfile) -> Vec<LintDiagnostic> {
+        lint_wgsl(source, profile)
+            .unwrap()
+            .into_iter()
+            .filter(|d| d.severity == LintSeverity::Error)
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_shader_has_no_errors() {
+        let source = r#"
+            @vertex
+            fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+                return vec4<f32>(f32(i), 0.0, 0.0, 1.0);
+            }
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                return vec4<f32>(1.0);
+            }
+        "#;
+        assert!(errors(source, &TargetProfile::WebGpuCore).is_empty());
+        assert!(errors(source, &TargetProfile::Compatibility).is_empty());
+    }
+
+    #[test]
+    fn test_parse_error_is_reported() {
+        assert!(lint_wgsl("fn broken(", &TargetProfile::WebGpuCore).is_err());
+    }
+
+    #[test]
+    fn test_oversized_workgroup() {
+        let source = "@compute @workgroup_size(512, 1, 1) fn main() {}";
+        let diagnostics = errors(source, &TargetProfile::WebGpuCore);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.message.contains("workgroup size x")));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.entry_point.as_deref() == Some("main")));
+    }
+
+    #[test]
+    fn test_bind_group_out_of_range() {
+        let source = r#"
+            @group(5) @binding(0) var<uniform> u: vec4<f32>;
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> { return u; }
+        "#;
+        let diagnostics = errors(source, &TargetProfile::WebGpuCore);
+        assert!(diagnostics.iter().any(|d| d.message.contains("@group(5)")));
+    }
+
+    #[test]
+    fn test_compat_restrictions() {
+        let source = r#"
+            @fragment
+            fn fs_main(@builtin(sample_index) s: u32) -> @location(0) vec4<f32> {
+                return vec4<f32>(f32(s));
+            }
+        "#;
+        assert!(errors(source, &TargetProfile::WebGpuCore).is_empty());
+        assert_eq!(errors(source, &TargetProfile::Compatibility).len(), 1);
+    }
+
+    #[test]
+    fn test_compat_limits() {
+        let limits = TargetProfile::Compatibility.limits();
+        assert_eq!(limits.max_compute_invocations_per_workgroup, 128);
+        assert_eq!(limits.max_color_attachments, 4);
+        assert_eq!(limits.max_bind_groups, Limits::default().max_bind_groups);
+
+        let source = "@compute @workgroup_size(256, 1, 1) fn main() {}";
+        assert!(errors(source, &TargetProfile::WebGpuCore).is_empty());
+        assert!(errors(source, &TargetProfile::Compatibility)
+            .iter()
+            .any(|d| d.message.contains("workgroup size x")));
+    }
+
+    #[test]
+    fn test_workgroup_storage_limit() {
+        let source = r#"
+            var<workgroup> tile: array<vec4<f32>, 2048>;
+            @compute @workgroup_size(64)
+            fn main(@builtin(local_invocation_index) i: u32) {
+                tile[i] = vec4<f32>(0.0);
+            }
+        "#;
+        let diagnostics = errors(source, &TargetProfile::WebGpuCore);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.message.contains("32768 bytes of workgroup storage")));
+    }
+
+    #[test]
+    fn test_diagnostic_display() {
+        let diagnostic = LintDiagnostic {
+            severity: LintSeverity::Warning,
+            entry_point: Some("main".to_string()),
+            message: "slow".to_string(),
+        };
+        assert_eq!(diagnostic.to_string(), "warning [main]: slow");
+    }
+}
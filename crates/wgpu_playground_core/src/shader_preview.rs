@@ -0,0 +1,367 @@
+//! Live preview of the shader editor's render shader
+//!
+//! The first vertex and fragment entry points are drawn without vertex
+//! buffers into a small texture, with a uniform buffer bound for every
+//! uniform the entry points use. The contents come from a
+//! [`UniformTweaker`] and are written into those buffers every frame, so
+//! dragging a slider shows up in the next frame. Shaders binding anything
+//! but uniform buffers can't be previewed.
+
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use naga::{AddressSpace, ShaderStage};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::operation_errors::OperationErrors;
+use crate::uniform_tweaker::UniformTweaker;
+use crate::upload_belt;
+
+/// Side of the preview texture until it is resized
+pub const PREVIEW_SIZE: u32 = 256;
+
+/// Format the fragment shader writes
+pub const PREVIEW_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Operation name of the pipeline build in [`OperationErrors`]
+const BUILD: &str = "preview";
+
+/// A uniform buffer bound for the preview
+struct BoundUniform {
+    group: u32,
+    binding: u32,
+    buffer: wgpu::Buffer,
+}
+
+/// Pipeline drawing a shader with its uniforms bound
+struct PreviewPipeline {
+    pipeline: wgpu::RenderPipeline,
+    uniforms: Vec<BoundUniform>,
+    /// Bind groups for every group up to the highest one used
+    bind_groups: Vec<wgpu::BindGroup>,
+}
+
+/// Renders the shader editor's shader into a texture
+pub struct ShaderPreview {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    size: (u32, u32),
+    pipeline: Option<PreviewPipeline>,
+    errors: OperationErrors,
+    /// Why the shader can't be previewed
+    error: Option<String>,
+    /// Vertices drawn per frame
+    pub vertex_count: u32,
+    #[cfg(not(target_arch = "wasm32"))]
+    texture_id: Option<egui::TextureId>,
+    /// The texture was replaced since it was registered with egui
+    #[cfg(not(target_arch = "wasm32"))]
+    texture_id_stale: bool,
+}
+
+impl ShaderPreview {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let size = (PREVIEW_SIZE, PREVIEW_SIZE);
+        let (texture, view) = create_target(device, size);
+        Self {
+            texture,
+            view,
+            size,
+            pipeline: None,
+            errors: OperationErrors::new(),
+            error: None,
+            vertex_count: 3,
+            #[cfg(not(target_arch = "wasm32"))]
+            texture_id: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            texture_id_stale: false,
+        }
+    }
+
+    /// Draw into a texture of `width`x`height` from now on
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        let size = (width.max(1), height.max(1));
+        if size == self.size {
+            return;
+        }
+        (self.texture, self.view) = create_target(device, size);
+        self.size = size;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.texture_id_stale = true;
+        }
+    }
+
+    /// Width and height of the preview texture
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Build the pipeline for a WGSL shader
+    ///
+    /// On failure the previous pipeline is dropped and [`error`](Self::error)
+    /// says why.
+    pub fn build(&mut self, device: &wgpu::Device, source: &str, uniforms: &UniformTweaker) {
+        self.pipeline = None;
+        self.error = self.try_build(device, source, uniforms).err();
+        self.take_device_error();
+    }
+
+    /// Turn a device error from the last build into the preview error
+    ///
+    /// On the web the error arrives after `build` returned, so this is
+    /// checked again every frame.
+    fn take_device_error(&mut self) {
+        if let Some(error) = self.errors.get(BUILD) {
+            self.errors.clear(BUILD);
+            self.error = Some(error.message);
+            self.pipeline = None;
+        }
+    }
+
+    fn try_build(
+        &mut self,
+        device: &wgpu::Device,
+        source: &str,
+        uniforms: &UniformTweaker,
+    ) -> Result<(), String> {
+        let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+        let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .map_err(|e| e.emit_to_string(source))?;
+
+        let entry_point = |stage| {
+            module
+                .entry_points
+                .iter()
+                .position(|entry| entry.stage == stage)
+        };
+        let (Some(vertex), Some(fragment)) = (
+            entry_point(ShaderStage::Vertex),
+            entry_point(ShaderStage::Fragment),
+        ) else {
+            return Err("The preview needs a @vertex and a @fragment entry point".to_string());
+        };
+
+        // Bindings the two entry points use; automatic layouts leave out
+        // the rest
+        let mut used = Vec::new();
+        for (handle, var) in module.global_variables.iter() {
+            let Some(resource) = &var.binding else {
+                continue;
+            };
+            if info.get_entry_point(vertex)[handle].is_empty()
+                && info.get_entry_point(fragment)[handle].is_empty()
+            {
+                continue;
+            }
+            if var.space != AddressSpace::Uniform {
+                return Err(format!(
+                    "The preview binds uniform buffers only; `{}` at @group({}) @binding({}) is not one",
+                    var.name.as_deref().unwrap_or("_"),
+                    resource.group,
+                    resource.binding
+                ));
+            }
+            used.push((resource.group, resource.binding));
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let entry_points = [
+            module.entry_points[vertex].name.as_str(),
+            module.entry_points[fragment].name.as_str(),
+        ];
+        let pipeline = self.errors.scoped(device, BUILD, || {
+            tracker.record(ApiCategory::Shader, "create_shader_module");
+            let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Shader Preview"),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+            tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+            let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Shader Preview Pipeline"),
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(entry_points[0]),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_points[1]),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: PREVIEW_FORMAT,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            });
+
+            let uniforms: Vec<BoundUniform> = used
+                .iter()
+                .filter_map(|&(group, binding)| {
+                    let block = uniforms.block(group, binding)?;
+                    tracker.record(ApiCategory::Buffer, "create_buffer");
+                    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some(&format!("Shader Preview {}", block.label())),
+                        size: block.size(),
+                        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    });
+                    Some(BoundUniform {
+                        group,
+                        binding,
+                        buffer,
+                    })
+                })
+                .collect();
+            let group_count = used.iter().map(|&(group, _)| group + 1).max().unwrap_or(0);
+            let bind_groups = (0..group_count)
+                .map(|group| {
+                    let entries: Vec<wgpu::BindGroupEntry> = uniforms
+                        .iter()
+                        .filter(|uniform| uniform.group == group)
+                        .map(|uniform| wgpu::BindGroupEntry {
+                            binding: uniform.binding,
+                            resource: uniform.buffer.as_entire_binding(),
+                        })
+                        .collect();
+                    tracker.record(ApiCategory::BindGroup, "create_bind_group");
+                    device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: Some("Shader Preview Bind Group"),
+                        layout: &pipeline.get_bind_group_layout(group),
+                        entries: &entries,
+                    })
+                })
+                .collect();
+            PreviewPipeline {
+                pipeline,
+                uniforms,
+                bind_groups,
+            }
+        });
+        self.pipeline = Some(pipeline);
+        Ok(())
+    }
+
+    /// Drop the pipeline, e.g. for a source that doesn't compose
+    pub fn set_error(&mut self, error: String) {
+        self.pipeline = None;
+        self.error = Some(error);
+    }
+
+    /// Why the shader can't be previewed
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Write the uniforms into their buffers and draw the shader
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        uniforms: &UniformTweaker,
+    ) {
+        self.take_device_error();
+        let Some(pipeline) = &self.pipeline else {
+            return;
+        };
+
+        for uniform in &pipeline.uniforms {
+            if let Some(block) = uniforms.block(uniform.group, uniform.binding) {
+                // A reflection newer than the pipeline may have resized it
+                if block.size() == uniform.buffer.size() {
+                    block.write(queue, &uniform.buffer);
+                }
+            }
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Shader Preview Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Shader Preview Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&pipeline.pipeline);
+            for (group, bind_group) in pipeline.bind_groups.iter().enumerate() {
+                render_pass.set_bind_group(group as u32, bind_group, &[]);
+            }
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..self.vertex_count, 0..1);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+
+    /// Texture the preview is drawn into
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    /// Get or register the preview texture with egui
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn texture_id(
+        &mut self,
+        device: &wgpu::Device,
+        renderer: &mut egui_wgpu::Renderer,
+    ) -> egui::TextureId {
+        if self.texture_id_stale {
+            if let Some(id) = self.texture_id.take() {
+                renderer.free_texture(&id);
+            }
+            self.texture_id_stale = false;
+        }
+        *self.texture_id.get_or_insert_with(|| {
+            renderer.register_native_texture(
+                device,
+                &self.view,
+                egui_wgpu::wgpu::FilterMode::Linear,
+            )
+        })
+    }
+}
+
+fn create_target(
+    device: &wgpu::Device,
+    (width, height): (u32, u32),
+) -> (wgpu::Texture, wgpu::TextureView) {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Shader Preview Texture"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: PREVIEW_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
@@ -0,0 +1,450 @@
+//! Cross-compilation of WGSL to the platform shading languages
+//!
+//! Runs a WGSL module through naga's backends the same way wgpu does when it
+//! creates a shader module on Vulkan (SPIR-V), D3D12 (HLSL), Metal (MSL) and
+//! OpenGL (GLSL). The output is meant for reading, so backend options stay at
+//! naga's defaults and missing bindings are faked instead of failing.
+//!
+//! SPIR-V is shown as a textual disassembly produced by [`disassemble_spirv`].
+
+use naga::back::{glsl, hlsl, msl, spv};
+use naga::valid::{Capabilities, ModuleInfo, ValidationFlags, Validator};
+use naga::Module;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// Shading language produced by a naga backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TargetLanguage {
+    SpirV,
+    Hlsl,
+    Msl,
+    Glsl,
+}
+
+impl TargetLanguage {
+    /// All supported targets
+    pub fn all() -> [TargetLanguage; 4] {
+        [
+            TargetLanguage::SpirV,
+            TargetLanguage::Hlsl,
+            TargetLanguage::Msl,
+            TargetLanguage::Glsl,
+        ]
+    }
+
+    /// Display name of the language
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetLanguage::SpirV => "SPIR-V",
+            TargetLanguage::Hlsl => "HLSL",
+            TargetLanguage::Msl => "MSL",
+            TargetLanguage::Glsl => "GLSL",
+        }
+    }
+
+    /// Graphics API that consumes this language
+    pub fn platform(&self) -> &'static str {
+        match self {
+            TargetLanguage::SpirV => "Vulkan",
+            TargetLanguage::Hlsl => "Direct3D 12",
+            TargetLanguage::Msl => "Metal",
+            TargetLanguage::Glsl => "OpenGL ES / WebGL",
+        }
+    }
+}
+
+/// Output of one backend
+#[derive(Debug, Clone)]
+pub struct Translation {
+    pub target: TargetLanguage,
+    /// Generated source, or the backend error
+    pub output: Result<String, String>,
+    /// Time spent in the backend
+    pub duration: Duration,
+}
+
+/// Parse and validate WGSL source
+///
+/// Errors are rendered with source snippets, like in the shader editor.
+pub fn parse_and_validate(source: &str) -> Result<(Module, ModuleInfo), String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|e| e.emit_to_string(source))?;
+    Ok((module, info))
+}
+
+/// Translate WGSL source to every target
+pub fn translate_all(source: &str) -> Result<Vec<Translation>, String> {
+    let (module, info) = parse_and_validate(source)?;
+    Ok(TargetLanguage::all()
+        .into_iter()
+        .map(|target| translate_module(&module, &info, target))
+        .collect())
+}
+
+/// Translate a validated module to one target
+pub fn translate_module(module: &Module, info: &ModuleInfo, target: TargetLanguage) -> Translation {
+    let start = Instant::now();
+    let output = match target {
+        TargetLanguage::SpirV => write_spirv(module, info),
+        TargetLanguage::Hlsl => write_hlsl(module, info),
+        TargetLanguage::Msl => write_msl(module, info),
+        TargetLanguage::Glsl => write_glsl(module, info),
+    };
+    Translation {
+        target,
+        output,
+        duration: start.elapsed(),
+    }
+}
+
+fn write_spirv(module: &Module, info: &ModuleInfo) -> Result<String, String> {
+    let words =
+        spv::write_vec(module, info, &spv::Options::default(), None).map_err(|e| e.to_string())?;
+    Ok(disassemble_spirv(&words))
+}
+
+fn write_hlsl(module: &Module, info: &ModuleInfo) -> Result<String, String> {
+    let options = hlsl::Options::default();
+    let pipeline_options = hlsl::PipelineOptions::default();
+    let mut output = String::new();
+    hlsl::Writer::new(&mut output, &options, &pipeline_options)
+        .write(module, info, None)
+        .map_err(|e| e.to_string())?;
+    Ok(output)
+}
+
+fn write_msl(module: &Module, info: &ModuleInfo) -> Result<String, String> {
+    let options = msl::Options {
+        lang_version: (2, 1),
+        ..Default::default()
+    };
+    msl::write_string(module, info, &options, &msl::PipelineOptions::default())
+        .map(|(output, _)| output)
+        .map_err(|e| e.to_string())
+}
+
+/// GLSL has no multi-entry-point modules, so each entry point is written as
+/// a separate shader
+fn write_glsl(module: &Module, info: &ModuleInfo) -> Result<String, String> {
+    if module.entry_points.is_empty() {
+        return Err("GLSL output requires at least one entry point".to_string());
+    }
+    let options = glsl::Options::default();
+    let mut output = String::new();
+    for entry_point in &module.entry_points {
+        let pipeline_options = glsl::PipelineOptions {
+            shader_stage: entry_point.stage,
+            entry_point: entry_point.name.clone(),
+            multiview: None,
+        };
+        let mut shader = String::new();
+        glsl::Writer::new(
+            &mut shader,
+            module,
+            info,
+            &options,
+            &pipeline_options,
+            naga::proc::BoundsCheckPolicies::default(),
+        )
+        .and_then(|mut writer| writer.write())
+        .map_err(|e| format!("{} ({:?}): {}", entry_point.name, entry_point.stage, e))?;
+        let _ = writeln!(
+            output,
+            "// ---- {} ({:?} stage) ----\n{}",
+            entry_point.name, entry_point.stage, shader
+        );
+    }
+    Ok(output)
+}
+
+/// Instructions that produce a result id without a result type
+fn has_result_id_only(op: spirv::Op) -> bool {
+    use spirv::Op;
+    match op {
+        Op::ExtInstImport | Op::String | Op::Label | Op::DecorationGroup => true,
+        Op::TypeForwardPointer => false,
+        _ => format!("{:?}", op).starts_with("Type"),
+    }
+}
+
+/// Instructions that produce neither a result type nor a result id
+fn has_no_result(op: spirv::Op) -> bool {
+    use spirv::Op;
+    matches!(
+        op,
+        Op::Nop
+            | Op::SourceContinued
+            | Op::Source
+            | Op::SourceExtension
+            | Op::Name
+            | Op::MemberName
+            | Op::Line
+            | Op::NoLine
+            | Op::Extension
+            | Op::MemoryModel
+            | Op::EntryPoint
+            | Op::ExecutionMode
+            | Op::ExecutionModeId
+            | Op::Capability
+            | Op::TypeForwardPointer
+            | Op::FunctionEnd
+            | Op::Store
+            | Op::CopyMemory
+            | Op::Decorate
+            | Op::DecorateId
+            | Op::DecorateString
+            | Op::MemberDecorate
+            | Op::MemberDecorateString
+            | Op::GroupDecorate
+            | Op::GroupMemberDecorate
+            | Op::ImageWrite
+            | Op::EmitVertex
+            | Op::EndPrimitive
+            | Op::ControlBarrier
+            | Op::MemoryBarrier
+            | Op::AtomicStore
+            | Op::LoopMerge
+            | Op::SelectionMerge
+            | Op::Branch
+            | Op::BranchConditional
+            | Op::Switch
+            | Op::Kill
+            | Op::TerminateInvocation
+            | Op::Return
+            | Op::ReturnValue
+            | Op::Unreachable
+    )
+}
+
+/// Position (after result type and id) where a trailing literal string starts
+fn string_operand_start(op: spirv::Op) -> Option<usize> {
+    use spirv::Op;
+    match op {
+        Op::ExtInstImport | Op::String | Op::SourceExtension | Op::Extension => Some(0),
+        Op::Name => Some(1),
+        Op::MemberName | Op::EntryPoint => Some(2),
+        _ => None,
+    }
+}
+
+/// How a non-string operand is printed
+enum Operand {
+    Id,
+    Literal,
+    Capability,
+    ExecutionModel,
+    StorageClass,
+    Decoration,
+}
+
+fn operand_kind(op: spirv::Op, position: usize) -> Operand {
+    use spirv::Op;
+    match (op, position) {
+        (Op::Capability, _) => Operand::Capability,
+        (Op::EntryPoint, 0) => Operand::ExecutionModel,
+        (Op::Variable | Op::TypePointer, 0) => Operand::StorageClass,
+        (Op::Decorate, 1) | (Op::MemberDecorate, 2) => Operand::Decoration,
+        (Op::Decorate | Op::ExecutionMode, 1..) => Operand::Literal,
+        (Op::MemberDecorate, 1..) => Operand::Literal,
+        (Op::Constant | Op::SpecConstant | Op::TypeInt | Op::TypeFloat, _) => Operand::Literal,
+        (Op::MemoryModel | Op::Source | Op::Function, _) => Operand::Literal,
+        (Op::TypeVector | Op::TypeMatrix | Op::ExtInst, 1) => Operand::Literal,
+        (Op::TypeImage, 1..) => Operand::Literal,
+        (Op::CompositeExtract, 1..) | (Op::CompositeInsert | Op::VectorShuffle, 2..) => {
+            Operand::Literal
+        }
+        (Op::SelectionMerge, 1) | (Op::LoopMerge, 2..) => Operand::Literal,
+        _ => Operand::Id,
+    }
+}
+
+fn write_operand(line: &mut String, kind: Operand, word: u32) {
+    let name = match kind {
+        Operand::Id => {
+            let _ = write!(line, " %{}", word);
+            return;
+        }
+        Operand::Literal => None,
+        Operand::Capability => spirv::Capability::from_u32(word).map(|v| format!("{:?}", v)),
+        Operand::ExecutionModel => {
+            spirv::ExecutionModel::from_u32(word).map(|v| format!("{:?}", v))
+        }
+        Operand::StorageClass => spirv::StorageClass::from_u32(word).map(|v| format!("{:?}", v)),
+        Operand::Decoration => spirv::Decoration::from_u32(word).map(|v| format!("{:?}", v)),
+    };
+    match name {
+        Some(name) => {
+            let _ = write!(line, " {}", name);
+        }
+        None => {
+            let _ = write!(line, " {}", word);
+        }
+    }
+}
+
+/// Decode a nul-terminated literal string, returning it and the word count used
+fn decode_string(words: &[u32]) -> (String, usize) {
+    let mut bytes = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        for byte in word.to_le_bytes() {
+            if byte == 0 {
+                return (String::from_utf8_lossy(&bytes).into_owned(), i + 1);
+            }
+            bytes.push(byte);
+        }
+    }
+    (String::from_utf8_lossy(&bytes).into_owned(), words.len())
+}
+
+/// Render a SPIR-V binary as text in the style of `spirv-dis`
+///
+/// Result ids are printed as `%N` on the left. Operands are decoded only as
+/// far as needed for a readable listing: literal strings are quoted, common
+/// enums (capabilities, execution models, storage classes, decorations) are
+/// named and everything else is shown as an id or a plain number.
+pub fn disassemble_spirv(words: &[u32]) -> String {
+    let mut out = String::new();
+    if words.len() < 5 || words[0] != spirv::MAGIC_NUMBER {
+        out.push_str("; not a SPIR-V module\n");
+        return out;
+    }
+    let _ = writeln!(out, "; SPIR-V");
+    let _ = writeln!(
+        out,
+        "; Version: {}.{}",
+        (words[1] >> 16) & 0xff,
+        (words[1] >> 8) & 0xff
+    );
+    let _ = writeln!(out, "; Generator: {:#010x}", words[2]);
+    let _ = writeln!(out, "; Bound: {}", words[3]);
+    let _ = writeln!(out, "; Schema: {}", words[4]);
+
+    let mut index = 5;
+    while index < words.len() {
+        let word_count = (words[index] >> 16) as usize;
+        let opcode = words[index] & 0xffff;
+        if word_count == 0 || index + word_count > words.len() {
+            let _ = writeln!(out, "; truncated instruction at word {}", index);
+            break;
+        }
+        let operands = &words[index + 1..index + word_count];
+        index += word_count;
+
+        let Some(op) = spirv::Op::from_u32(opcode) else {
+            let _ = writeln!(out, "{:>15}Op{} {:?}", "", opcode, operands);
+            continue;
+        };
+        if op == spirv::Op::Function {
+            out.push('\n');
+        }
+
+        let (result_type, result_id, rest) = if has_no_result(op) {
+            (None, None, operands)
+        } else if has_result_id_only(op) && !operands.is_empty() {
+            (None, Some(operands[0]), &operands[1..])
+        } else if operands.len() >= 2 {
+            (Some(operands[0]), Some(operands[1]), &operands[2..])
+        } else {
+            (None, None, operands)
+        };
+
+        let mut line = match result_id {
+            Some(id) => format!("{:>12} = Op{:?}", format!("%{}", id), op),
+            None => format!("{:>15}Op{:?}", "", op),
+        };
+        if let Some(ty) = result_type {
+            let _ = write!(line, " %{}", ty);
+        }
+
+        let mut position = 0;
+        while position < rest.len() {
+            if string_operand_start(op) == Some(position) {
+                let (text, used) = decode_string(&rest[position..]);
+                let _ = write!(line, " \"{}\"", text);
+                position += used;
+                continue;
+            }
+            write_operand(&mut line, operand_kind(op, position), rest[position]);
+            position += 1;
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADER: &str = r#"
+@group(0) @binding(0) var<uniform> tint: vec4<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let x = f32(index) - 1.0;
+    return vec4<f32>(x, 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return tint;
+}
+"#;
+
+    #[test]
+    fn test_translate_all_targets() {
+        let translations = translate_all(SHADER).unwrap();
+        assert_eq!(translations.len(), 4);
+        for translation in &translations {
+            let output = translation
+                .output
+                .as_ref()
+                .unwrap_or_else(|e| panic!("{} failed: {}", translation.target.name(), e));
+            assert!(!output.is_empty());
+        }
+
+        let output = |target: TargetLanguage| {
+            translations
+                .iter()
+                .find(|t| t.target == target)
+                .unwrap()
+                .output
+                .clone()
+                .unwrap()
+        };
+        assert!(output(TargetLanguage::SpirV).contains("OpEntryPoint Vertex"));
+        assert!(output(TargetLanguage::Hlsl).contains("SV_Position"));
+        assert!(output(TargetLanguage::Msl).contains("metal_stdlib"));
+        let glsl = output(TargetLanguage::Glsl);
+        assert!(glsl.contains("// ---- vs_main (Vertex stage) ----"));
+        assert!(glsl.contains("// ---- fs_main (Fragment stage) ----"));
+    }
+
+    #[test]
+    fn test_invalid_source_reports_error() {
+        let error = translate_all("fn broken( {").unwrap_err();
+        assert!(!error.is_empty());
+    }
+
+    #[test]
+    fn test_disassemble_spirv() {
+        let (module, info) = parse_and_validate(SHADER).unwrap();
+        let words = spv::write_vec(&module, &info, &spv::Options::default(), None).unwrap();
+        let text = disassemble_spirv(&words);
+
+        assert!(text.starts_with("; SPIR-V\n; Version: 1.0"));
+        assert!(text.contains("OpCapability Shader"));
+        assert!(text.contains("OpEntryPoint Vertex %"));
+        assert!(text.contains("\"vs_main\""));
+        assert!(text.contains("OpDecorate %"));
+        assert!(text.contains("= OpTypeFloat 32"));
+        assert!(text.contains("OpVariable %"));
+        assert!(text.contains("OpFunctionEnd"));
+
+        assert_eq!(disassemble_spirv(&[1, 2, 3]), "; not a SPIR-V module\n");
+    }
+}
@@ -0,0 +1,214 @@
+use crate::shader_translation::{translate_all, TargetLanguage, Translation};
+
+/// UI panel showing what WGSL lowers to on each platform
+pub struct ShaderTranslationPanel {
+    source: String,
+    /// Follow the shader editor and retranslate whenever its source changes
+    follow_editor: bool,
+    /// Source the current translations were produced from
+    translated_source: Option<String>,
+    translations: Vec<Translation>,
+    error: Option<String>,
+    /// Targets shown next to the source, left to right
+    visible: Vec<TargetLanguage>,
+}
+
+impl Default for ShaderTranslationPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShaderTranslationPanel {
+    /// Create a new panel following the shader editor
+    pub fn new() -> Self {
+        Self {
+            source: String::new(),
+            follow_editor: true,
+            translated_source: None,
+            translations: Vec::new(),
+            error: None,
+            visible: vec![TargetLanguage::SpirV, TargetLanguage::Hlsl],
+        }
+    }
+
+    /// Provide the shader editor's source; it is picked up by the next
+    /// [`update`](Self::update) if the panel follows the editor
+    pub fn sync_source(&mut self, source: &str) {
+        if self.follow_editor && self.source != source {
+            self.source = source.to_string();
+        }
+    }
+
+    /// Translate the current source if it changed since the last translation
+    pub fn update(&mut self) {
+        if self.translated_source.as_deref() == Some(self.source.as_str()) {
+            return;
+        }
+        self.translate();
+    }
+
+    /// Translate the current source to all targets
+    pub fn translate(&mut self) {
+        match translate_all(&self.source) {
+            Ok(translations) => {
+                self.translations = translations;
+                self.error = None;
+            }
+            Err(e) => {
+                // Keep the last good output so it stays readable while typing
+                self.error = Some(e);
+            }
+        }
+        self.translated_source = Some(self.source.clone());
+    }
+
+    /// Translation for a target, if any
+    pub fn translation(&self, target: TargetLanguage) -> Option<&Translation> {
+        self.translations.iter().find(|t| t.target == target)
+    }
+
+    /// Render the panel UI
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.update();
+
+        ui.heading("🔀 Shader Translation");
+        ui.label(
+            "See what your WGSL lowers to on each platform. naga translates the shader to \
+             SPIR-V (Vulkan), HLSL (Direct3D 12), MSL (Metal) and GLSL (OpenGL/WebGL).",
+        );
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.follow_editor, "Follow shader editor")
+                .on_hover_text("Retranslate whenever the shader editor's source changes");
+            if ui.button("🔄 Translate").clicked() {
+                self.translate();
+            }
+            ui.separator();
+            ui.label("Show:");
+            for target in TargetLanguage::all() {
+                let mut shown = self.visible.contains(&target);
+                if ui
+                    .checkbox(&mut shown, target.name())
+                    .on_hover_text(target.platform())
+                    .changed()
+                {
+                    if shown {
+                        self.visible.push(target);
+                        self.visible
+                            .sort_by_key(|t| TargetLanguage::all().iter().position(|a| a == t));
+                    } else {
+                        self.visible.retain(|t| *t != target);
+                    }
+                }
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(
+                egui::Color32::from_rgb(220, 80, 80),
+                "✗ The shader does not compile; showing the last successful translation",
+            );
+            egui::CollapsingHeader::new("Error details")
+                .id_salt("shader_translation_error")
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new(error).monospace());
+                });
+        }
+        ui.add_space(4.0);
+
+        let columns = 1 + self.visible.len();
+        let height = ui.available_height();
+        ui.columns(columns, |columns| {
+            columns[0].strong("WGSL");
+            columns[0].add_space(2.0);
+            egui::ScrollArea::both()
+                .id_salt("shader_translation_source")
+                .max_height(height - 24.0)
+                .show(&mut columns[0], |ui| {
+                    let response = ui.add(
+                        egui::TextEdit::multiline(&mut self.source)
+                            .code_editor()
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(30),
+                    );
+                    if response.changed() {
+                        // Editing here detaches the panel from the shader editor
+                        self.follow_editor = false;
+                    }
+                });
+
+            for (i, target) in self.visible.iter().enumerate() {
+                let ui = &mut columns[i + 1];
+                let translation = self.translations.iter().find(|t| t.target == *target);
+                ui.horizontal(|ui| {
+                    ui.strong(target.name());
+                    if let Some(translation) = translation {
+                        ui.weak(format!(
+                            "{} · {:.2} ms",
+                            target.platform(),
+                            translation.duration.as_secs_f64() * 1000.0
+                        ));
+                    }
+                });
+                ui.add_space(2.0);
+                egui::ScrollArea::both()
+                    .id_salt(("shader_translation_output", i))
+                    .max_height(height - 24.0)
+                    .show(ui, |ui| match translation.map(|t| &t.output) {
+                        Some(Ok(output)) => {
+                            let mut text = output.as_str();
+                            ui.add(
+                                egui::TextEdit::multiline(&mut text)
+                                    .code_editor()
+                                    .desired_width(f32::INFINITY),
+                            );
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(220, 80, 80),
+                                format!("✗ {} backend failed: {}", target.name(), e),
+                            );
+                        }
+                        None => {
+                            ui.weak("No output yet");
+                        }
+                    });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADER: &str = r#"
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+}
+"#;
+
+    #[test]
+    fn test_follows_editor_and_keeps_last_good_output() {
+        let mut panel = ShaderTranslationPanel::new();
+        panel.sync_source(SHADER);
+        panel.update();
+        assert!(panel.error.is_none());
+        assert!(panel
+            .translation(TargetLanguage::Msl)
+            .unwrap()
+            .output
+            .is_ok());
+
+        panel.sync_source("fn broken(");
+        panel.update();
+        assert!(panel.error.is_some());
+        assert!(panel.translation(TargetLanguage::Hlsl).is_some());
+
+        panel.follow_editor = false;
+        panel.sync_source(SHADER);
+        assert_eq!(panel.source, "fn broken(");
+    }
+}
@@ -0,0 +1,588 @@
+//! Shader permutations driven by boolean and integer defines
+//!
+//! An ubershader declares defines (e.g. `USE_FOG: bool`, `LIGHT_COUNT: 1,2,4`)
+//! and guards code with preprocessor directives:
+//!
+//! ```text
+//! #if USE_FOG
+//!     color = apply_fog(color);
+//! #endif
+//! #if LIGHT_COUNT >= 2
+//!     color += shade(lights[1]);
+//! #endif
+//! ```
+//!
+//! [`permutations`] expands the defines into every combination and
+//! [`compile_all`] preprocesses, validates and compiles each variant with naga,
+//! recording sizes and timings.
+//!
+//! Supported directives: `#if EXPR`, `#elif EXPR`, `#ifdef NAME`,
+//! `#ifndef NAME`, `#else`, `#endif`. `EXPR` is `NAME`, `!NAME` or
+//! `NAME OP INTEGER` with `OP` one of `== != < <= > >=`. `#ifdef` and
+//! `#ifndef` only test whether the permutation defines `NAME`, whatever its
+//! value, so a define set to `0` or `false` still counts as defined and an
+//! unknown name is simply undefined. Every define is also
+//! appended to the output as a WGSL `const`, so its value can be used directly
+//! in code. Removed lines are kept as blank lines so that error line numbers
+//! match the original source.
+
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Upper bound on the number of permutations compiled in one go
+pub const MAX_PERMUTATIONS: usize = 256;
+
+/// Possible values of a define
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefineValues {
+    /// `false` and `true`
+    Bool,
+    /// An explicit list of integers
+    Int(Vec<i64>),
+}
+
+/// A define that varies between permutations
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderDefine {
+    /// Name used in directives and as WGSL constant
+    pub name: String,
+    /// Values the define takes
+    pub values: DefineValues,
+}
+
+impl ShaderDefine {
+    /// Create a boolean define
+    pub fn boolean(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            values: DefineValues::Bool,
+        }
+    }
+
+    /// Create an integer define with the given values
+    pub fn int(name: impl Into<String>, values: Vec<i64>) -> Self {
+        Self {
+            name: name.into(),
+            values: DefineValues::Int(values),
+        }
+    }
+
+    fn value_list(&self) -> Vec<DefineValue> {
+        match &self.values {
+            DefineValues::Bool => vec![DefineValue::Bool(false), DefineValue::Bool(true)],
+            DefineValues::Int(values) => values.iter().map(|&v| DefineValue::Int(v)).collect(),
+        }
+    }
+}
+
+/// Value of a define in one permutation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefineValue {
+    Bool(bool),
+    Int(i64),
+}
+
+impl DefineValue {
+    fn as_i64(self) -> i64 {
+        match self {
+            Self::Bool(b) => b as i64,
+            Self::Int(v) => v,
+        }
+    }
+
+    fn wgsl_const(self, name: &str) -> String {
+        match self {
+            Self::Bool(b) => format!("const {}: bool = {};", name, b),
+            Self::Int(v) => format!("const {}: i32 = {};", name, v),
+        }
+    }
+}
+
+impl fmt::Display for DefineValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Int(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// One combination of define values, in declaration order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Permutation {
+    values: Vec<(String, DefineValue)>,
+}
+
+impl Permutation {
+    /// Create a permutation from explicit values
+    pub fn new(values: Vec<(String, DefineValue)>) -> Self {
+        Self { values }
+    }
+
+    /// Look up the value of a define
+    pub fn get(&self, name: &str) -> Option<DefineValue> {
+        self.values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| *value)
+    }
+
+    /// All define values
+    pub fn values(&self) -> &[(String, DefineValue)] {
+        &self.values
+    }
+
+    /// Short label such as `USE_FOG=true LIGHT_COUNT=4`
+    pub fn label(&self) -> String {
+        if self.values.is_empty() {
+            return "(default)".to_string();
+        }
+        self.values
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Expand defines into every combination of their values
+///
+/// The first define varies slowest. With no defines, a single empty
+/// permutation is returned.
+pub fn permutations(defines: &[ShaderDefine]) -> Vec<Permutation> {
+    let mut result = vec![Permutation::default()];
+    for define in defines {
+        let values = define.value_list();
+        result = result
+            .into_iter()
+            .flat_map(|base| {
+                values.iter().map(move |value| {
+                    let mut next = base.clone();
+                    next.values.push((define.name.clone(), *value));
+                    next
+                })
+            })
+            .collect();
+    }
+    result
+}
+
+/// Number of permutations the defines expand to
+pub fn permutation_count(defines: &[ShaderDefine]) -> usize {
+    defines
+        .iter()
+        .map(|d| match &d.values {
+            DefineValues::Bool => 2,
+            DefineValues::Int(values) => values.len(),
+        })
+        .product()
+}
+
+/// Error produced while preprocessing a variant
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreprocessError {
+    /// Line number (1-indexed)
+    pub line: usize,
+    /// Error description
+    pub message: String,
+}
+
+impl fmt::Display for PreprocessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for PreprocessError {}
+
+/// State of one `#if` block
+struct Conditional {
+    /// Whether the enclosing block is active
+    parent_active: bool,
+    /// Whether the current branch is active
+    active: bool,
+    /// Whether any branch has been taken
+    taken: bool,
+    /// Whether `#else` has been seen
+    seen_else: bool,
+}
+
+/// Resolve directives for one permutation
+pub fn preprocess(source: &str, permutation: &Permutation) -> Result<String, PreprocessError> {
+    let mut stack: Vec<Conditional> = Vec::new();
+    let mut output = String::with_capacity(source.len());
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |message: String| PreprocessError {
+            line: line_number,
+            message,
+        };
+        let active = stack.last().is_none_or(|c| c.active);
+        let trimmed = line.trim_start();
+
+        let Some(directive) = trimmed.strip_prefix('#') else {
+            if active {
+                output.push_str(line);
+            }
+            output.push('\n');
+            continue;
+        };
+
+        let (keyword, argument) = directive
+            .split_once(char::is_whitespace)
+            .map(|(k, a)| (k, a.trim()))
+            .unwrap_or((directive.trim(), ""));
+
+        match keyword {
+            "if" | "ifdef" | "ifndef" => {
+                let condition = match keyword {
+                    "if" => evaluate(argument, permutation).map_err(error)?,
+                    "ifdef" => is_defined(argument, permutation).map_err(error)?,
+                    _ => !is_defined(argument, permutation).map_err(error)?,
+                };
+                stack.push(Conditional {
+                    parent_active: active,
+                    active: active && condition,
+                    taken: condition,
+                    seen_else: false,
+                });
+            }
+            "elif" => {
+                let top = stack
+                    .last_mut()
+                    .ok_or_else(|| error("#elif without #if".to_string()))?;
+                if top.seen_else {
+                    return Err(error("#elif after #else".to_string()));
+                }
+                let condition = !top.taken && evaluate(argument, permutation).map_err(error)?;
+                top.active = top.parent_active && condition;
+                top.taken |= condition;
+            }
+            "else" => {
+                let top = stack
+                    .last_mut()
+                    .ok_or_else(|| error("#else without #if".to_string()))?;
+                if top.seen_else {
+                    return Err(error("duplicate #else".to_string()));
+                }
+                top.seen_else = true;
+                top.active = top.parent_active && !top.taken;
+                top.taken = true;
+            }
+            "endif" => {
+                stack
+                    .pop()
+                    .ok_or_else(|| error("#endif without #if".to_string()))?;
+            }
+            other => return Err(error(format!("unknown directive #{}", other))),
+        }
+        output.push('\n');
+    }
+
+    if !stack.is_empty() {
+        return Err(PreprocessError {
+            line: source.lines().count(),
+            message: format!("{} unterminated #if block(s)", stack.len()),
+        });
+    }
+
+    for (name, value) in permutation.values() {
+        output.push_str(&value.wgsl_const(name));
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Whether the permutation defines `name`, for `#ifdef` and `#ifndef`
+fn is_defined(name: &str, permutation: &Permutation) -> Result<bool, String> {
+    if name.is_empty() {
+        return Err("missing define name".to_string());
+    }
+    if name.contains(char::is_whitespace) {
+        return Err(format!("expected a single define name, got '{}'", name));
+    }
+    Ok(permutation.get(name).is_some())
+}
+
+/// Evaluate a directive condition
+fn evaluate(expression: &str, permutation: &Permutation) -> Result<bool, String> {
+    let lookup = |name: &str| {
+        permutation
+            .get(name)
+            .map(DefineValue::as_i64)
+            .ok_or_else(|| format!("unknown define '{}'", name))
+    };
+
+    if let Some(name) = expression.strip_prefix('!') {
+        return Ok(lookup(name.trim())? == 0);
+    }
+
+    for op in ["==", "!=", "<=", ">=", "<", ">"] {
+        if let Some((lhs, rhs)) = expression.split_once(op) {
+            let lhs = lookup(lhs.trim())?;
+            let rhs: i64 = rhs
+                .trim()
+                .parse()
+                .map_err(|_| format!("expected an integer after '{}'", op))?;
+            return Ok(match op {
+                "==" => lhs == rhs,
+                "!=" => lhs != rhs,
+                "<=" => lhs <= rhs,
+                ">=" => lhs >= rhs,
+                "<" => lhs < rhs,
+                _ => lhs > rhs,
+            });
+        }
+    }
+
+    if expression.is_empty() {
+        return Err("missing condition".to_string());
+    }
+    Ok(lookup(expression)? != 0)
+}
+
+/// Size statistics of a successfully compiled variant
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantStats {
+    /// Size of the preprocessed WGSL in bytes
+    pub wgsl_bytes: usize,
+    /// Size of the generated SPIR-V in bytes
+    pub spirv_bytes: usize,
+    /// Number of entry points
+    pub entry_points: usize,
+    /// Number of functions, excluding entry points
+    pub functions: usize,
+}
+
+/// Compilation result of one permutation
+#[derive(Debug, Clone)]
+pub struct VariantReport {
+    /// Define values of the variant
+    pub permutation: Permutation,
+    /// Statistics, or an error message if any stage failed
+    pub result: Result<VariantStats, String>,
+    /// Time spent parsing and validating with naga
+    pub compile_time: Duration,
+    /// Time spent generating SPIR-V
+    pub codegen_time: Duration,
+}
+
+impl VariantReport {
+    /// Whether the variant compiled
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Preprocess, validate and compile a single permutation
+pub fn compile_variant(source: &str, permutation: &Permutation) -> VariantReport {
+    let mut report = VariantReport {
+        permutation: permutation.clone(),
+        result: Err(String::new()),
+        compile_time: Duration::ZERO,
+        codegen_time: Duration::ZERO,
+    };
+
+    let wgsl = match preprocess(source, permutation) {
+        Ok(wgsl) => wgsl,
+        Err(e) => {
+            report.result = Err(format!("Preprocessor: {}", e));
+            return report;
+        }
+    };
+
+    let start = Instant::now();
+    let module = naga::front::wgsl::parse_str(&wgsl).map_err(|e| e.emit_to_string(&wgsl));
+    let info = module.as_ref().map_err(Clone::clone).and_then(|module| {
+        Validator::new(ValidationFlags::all(), Capabilities::default())
+            .validate(module)
+            .map_err(|e| e.emit_to_string(&wgsl))
+    });
+    report.compile_time = start.elapsed();
+
+    let (module, info) = match (module, info) {
+        (Ok(module), Ok(info)) => (module, info),
+        (Err(e), _) | (_, Err(e)) => {
+            report.result = Err(e);
+            return report;
+        }
+    };
+
+    let start = Instant::now();
+    let spirv =
+        naga::back::spv::write_vec(&module, &info, &naga::back::spv::Options::default(), None);
+    report.codegen_time = start.elapsed();
+
+    report.result = spirv
+        .map(|words| VariantStats {
+            wgsl_bytes: wgsl.len(),
+            spirv_bytes: words.len() * 4,
+            entry_points: module.entry_points.len(),
+            functions: module.functions.len(),
+        })
+        .map_err(|e| format!("SPIR-V: {}", e));
+    report
+}
+
+/// Compile every permutation of the defines
+///
+/// Fails without compiling anything if the defines expand to more than
+/// [`MAX_PERMUTATIONS`] variants.
+pub fn compile_all(source: &str, defines: &[ShaderDefine]) -> Result<Vec<VariantReport>, String> {
+    let count = permutation_count(defines);
+    if count > MAX_PERMUTATIONS {
+        return Err(format!(
+            "{} permutations exceed the limit of {}",
+            count, MAX_PERMUTATIONS
+        ));
+    }
+    Ok(permutations(defines)
+        .iter()
+        .map(|permutation| compile_variant(source, permutation))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UBERSHADER: &str = r#"
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    var color = vec3<f32>(0.5);
+#if USE_FOG
+    color = mix(color, vec3<f32>(0.8), 0.3);
+#endif
+#if LIGHT_COUNT >= 2
+    color = color * f32(LIGHT_COUNT);
+#else
+    color = color * 0.5;
+#endif
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+    fn defines() -> Vec<ShaderDefine> {
+        vec![
+            ShaderDefine::boolean("USE_FOG"),
+            ShaderDefine::int("LIGHT_COUNT", vec![1, 2, 4]),
+        ]
+    }
+
+    #[test]
+    fn test_permutations() {
+        let perms = permutations(&defines());
+        assert_eq!(perms.len(), 6);
+        assert_eq!(permutation_count(&defines()), 6);
+        assert_eq!(perms[0].label(), "USE_FOG=false LIGHT_COUNT=1");
+        assert_eq!(perms[5].label(), "USE_FOG=true LIGHT_COUNT=4");
+        assert_eq!(permutations(&[]).len(), 1);
+    }
+
+    #[test]
+    fn test_preprocess_keeps_line_numbers() {
+        let perm = Permutation::new(vec![
+            ("USE_FOG".to_string(), DefineValue::Bool(false)),
+            ("LIGHT_COUNT".to_string(), DefineValue::Int(4)),
+        ]);
+        let output = preprocess(UBERSHADER, &perm).unwrap();
+        assert!(!output.contains("mix("));
+        assert!(output.contains("f32(LIGHT_COUNT)"));
+        assert!(!output.contains("0.5;\n"));
+        assert!(output.contains("const LIGHT_COUNT: i32 = 4;"));
+        assert_eq!(
+            output.lines().position(|l| l.contains("f32(LIGHT_COUNT)")),
+            UBERSHADER
+                .lines()
+                .position(|l| l.contains("f32(LIGHT_COUNT)"))
+        );
+    }
+
+    #[test]
+    fn test_preprocess_errors() {
+        let perm = Permutation::default();
+        assert!(preprocess("#if\n#endif", &perm).is_err());
+        assert!(preprocess("#if UNKNOWN\n#endif", &perm).is_err());
+        assert!(preprocess("#endif", &perm).is_err());
+        let err = preprocess(
+            "\n#ifdef A",
+            &Permutation::new(vec![("A".into(), DefineValue::Bool(true))]),
+        )
+        .unwrap_err();
+        assert!(err.message.contains("unterminated"));
+    }
+
+    #[test]
+    fn test_ifdef_tests_presence() {
+        let source = "#ifdef ZERO\nzero\n#endif\n#ifndef ZERO\nnot_zero\n#endif\n\
+                      #ifdef MISSING\nmissing\n#endif\n#ifndef MISSING\nnot_missing\n#endif";
+        let perm = Permutation::new(vec![("ZERO".to_string(), DefineValue::Int(0))]);
+        let output = preprocess(source, &perm).unwrap();
+        let kept: Vec<&str> = output
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with("const"))
+            .collect();
+        assert_eq!(kept, ["zero", "not_missing"]);
+
+        // `#if` still tests the value and rejects unknown names
+        assert!(preprocess("#if ZERO\nzero\n#endif", &perm)
+            .unwrap()
+            .lines()
+            .all(|l| l != "zero"));
+        assert!(preprocess("#if MISSING\n#endif", &perm).is_err());
+        assert!(preprocess("#ifdef\n#endif", &perm).is_err());
+    }
+
+    #[test]
+    fn test_nested_and_elif() {
+        let source =
+            "#if A\n#if B == 2\nab2\n#elif B == 3\nab3\n#else\nab\n#endif\n#else\nnot_a\n#endif";
+        let perm = |a: bool, b: i64| {
+            Permutation::new(vec![
+                ("A".to_string(), DefineValue::Bool(a)),
+                ("B".to_string(), DefineValue::Int(b)),
+            ])
+        };
+        let body = |p: Permutation| {
+            let out = preprocess(source, &p).unwrap();
+            out.lines()
+                .filter(|l| !l.is_empty() && !l.starts_with("const"))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        assert_eq!(body(perm(true, 2)), "ab2");
+        assert_eq!(body(perm(true, 3)), "ab3");
+        assert_eq!(body(perm(true, 9)), "ab");
+        assert_eq!(body(perm(false, 2)), "not_a");
+    }
+
+    #[test]
+    fn test_compile_all() {
+        let reports = compile_all(UBERSHADER, &defines()).unwrap();
+        assert_eq!(reports.len(), 6);
+        for report in &reports {
+            let stats = report.result.as_ref().unwrap();
+            assert!(stats.spirv_bytes > 0);
+            assert_eq!(stats.entry_points, 1);
+        }
+    }
+
+    #[test]
+    fn test_compile_reports_failing_variant() {
+        let source =
+            "#if BROKEN\nthis is not wgsl\n#endif\n@compute @workgroup_size(1) fn main() {}";
+        let reports = compile_all(source, &[ShaderDefine::boolean("BROKEN")]).unwrap();
+        assert!(reports[0].is_ok());
+        assert!(!reports[1].is_ok());
+    }
+
+    #[test]
+    fn test_too_many_permutations() {
+        let defines: Vec<ShaderDefine> = (0..9)
+            .map(|i| ShaderDefine::boolean(format!("D{}", i)))
+            .collect();
+        assert!(compile_all("", &defines).is_err());
+    }
+}
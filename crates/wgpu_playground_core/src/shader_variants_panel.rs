@@ -0,0 +1,433 @@
+use crate::operation_errors::OperationErrors;
+use crate::shader_variants::{
+    compile_all, permutation_count, preprocess, DefineValues, ShaderDefine, VariantReport,
+    MAX_PERMUTATIONS,
+};
+use std::time::{Duration, Instant};
+
+/// Editable define row
+#[derive(Debug, Clone)]
+struct DefineInput {
+    name: String,
+    is_bool: bool,
+    /// Comma-separated integer values
+    values: String,
+}
+
+impl DefineInput {
+    fn to_define(&self) -> Result<ShaderDefine, String> {
+        let name = self.name.trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Invalid define name '{}'", self.name));
+        }
+        if self.is_bool {
+            return Ok(ShaderDefine::boolean(name));
+        }
+        let values = self
+            .values
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                v.parse::<i64>()
+                    .map_err(|_| format!("'{}' is not an integer (define {})", v, name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.is_empty() {
+            return Err(format!("Define {} has no values", name));
+        }
+        Ok(ShaderDefine::int(name, values))
+    }
+}
+
+/// A compiled variant plus the optional device compile time
+struct VariantRow {
+    report: VariantReport,
+    device_time: Option<Duration>,
+    /// Error reported by the device when creating the module
+    device_error: Option<String>,
+}
+
+/// Operation name of per-variant module creation in [`OperationErrors`]
+const DEVICE_COMPILE: &str = "device_compile";
+
+/// UI panel for designing ubershaders with define permutations
+pub struct ShaderVariantsPanel {
+    source: String,
+    defines: Vec<DefineInput>,
+    compile_on_device: bool,
+    rows: Vec<VariantRow>,
+    selected_variant: Option<usize>,
+    status_message: Option<String>,
+}
+
+impl Default for ShaderVariantsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShaderVariantsPanel {
+    /// Create a new panel with an example ubershader
+    pub fn new() -> Self {
+        Self {
+            source: Self::example_source(),
+            defines: vec![
+                DefineInput {
+                    name: "USE_FOG".to_string(),
+                    is_bool: true,
+                    values: String::new(),
+                },
+                DefineInput {
+                    name: "LIGHT_COUNT".to_string(),
+                    is_bool: false,
+                    values: "1, 2, 4".to_string(),
+                },
+            ],
+            compile_on_device: false,
+            rows: Vec::new(),
+            selected_variant: None,
+            status_message: None,
+        }
+    }
+
+    fn example_source() -> String {
+        r#"// Ubershader example: toggle features with #if / #else / #endif
+@fragment
+fn fs_main(@builtin(position) pos: vec4<f32>) -> @location(0) vec4<f32> {
+    var color = vec3<f32>(0.2, 0.4, 0.8);
+#if LIGHT_COUNT >= 2
+    color = color * (1.0 + 0.25 * f32(LIGHT_COUNT));
+#endif
+#if USE_FOG
+    let fog = clamp(pos.y / 600.0, 0.0, 1.0);
+    color = mix(color, vec3<f32>(0.7, 0.7, 0.75), fog);
+#endif
+    return vec4<f32>(color, 1.0);
+}
+"#
+        .to_string()
+    }
+
+    fn collect_defines(&self) -> Result<Vec<ShaderDefine>, String> {
+        self.defines.iter().map(DefineInput::to_define).collect()
+    }
+
+    /// Compile every permutation
+    pub fn compile_all(&mut self, device: Option<&wgpu::Device>) {
+        self.rows.clear();
+        self.selected_variant = None;
+
+        let defines = match self.collect_defines() {
+            Ok(defines) => defines,
+            Err(e) => {
+                self.status_message = Some(format!("✗ {}", e));
+                return;
+            }
+        };
+
+        let reports = match compile_all(&self.source, &defines) {
+            Ok(reports) => reports,
+            Err(e) => {
+                self.status_message = Some(format!("✗ {}", e));
+                return;
+            }
+        };
+
+        let errors = OperationErrors::new();
+        self.rows = reports
+            .into_iter()
+            .map(|report| {
+                let device_result = device
+                    .filter(|_| self.compile_on_device && report.is_ok())
+                    .and_then(|device| {
+                        Self::device_compile(device, &self.source, &report, &errors)
+                    });
+                let (device_time, device_error) = match device_result {
+                    Some((time, error)) => (Some(time), error),
+                    None => (None, None),
+                };
+                VariantRow {
+                    report,
+                    device_time,
+                    device_error,
+                }
+            })
+            .collect();
+
+        let failed = self
+            .rows
+            .iter()
+            .filter(|r| !r.report.is_ok() || r.device_error.is_some())
+            .count();
+        self.status_message = Some(if failed == 0 {
+            format!("✓ All {} variants compiled", self.rows.len())
+        } else {
+            format!("✗ {} of {} variants failed", failed, self.rows.len())
+        });
+    }
+
+    /// Time `create_shader_module` for a variant that already passed naga validation
+    ///
+    /// Also returns the error if the device rejected the module.
+    fn device_compile(
+        device: &wgpu::Device,
+        source: &str,
+        report: &VariantReport,
+        errors: &OperationErrors,
+    ) -> Option<(Duration, Option<String>)> {
+        let wgsl = preprocess(source, &report.permutation).ok()?;
+        let start = Instant::now();
+        let _module = errors.scoped(device, DEVICE_COMPILE, || {
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(&report.permutation.label()),
+                source: wgpu::ShaderSource::Wgsl(wgsl.into()),
+            })
+        });
+        let elapsed = start.elapsed();
+        Some((elapsed, errors.get(DEVICE_COMPILE).map(|e| e.message)))
+    }
+
+    /// Render the panel
+    pub fn ui(&mut self, ui: &mut egui::Ui, device: Option<&wgpu::Device>) {
+        ui.heading("🧬 Shader Variants");
+        ui.separator();
+        ui.label("Guard ubershader features with #if / #elif / #else / #endif and compile every combination of defines.");
+        ui.add_space(10.0);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            self.render_defines(ui);
+            ui.add_space(10.0);
+
+            ui.label("Shader Source:");
+            ui.add(
+                egui::TextEdit::multiline(&mut self.source)
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(14),
+            );
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("⚙️ Compile All Variants").clicked() {
+                    self.compile_all(device);
+                }
+                ui.add_enabled(
+                    device.is_some(),
+                    egui::Checkbox::new(&mut self.compile_on_device, "Also create on device"),
+                )
+                .on_hover_text("Measure create_shader_module time for each valid variant");
+            });
+
+            if let Some(msg) = &self.status_message {
+                let color = if msg.starts_with('✓') {
+                    egui::Color32::from_rgb(100, 200, 100)
+                } else {
+                    egui::Color32::from_rgb(220, 80, 80)
+                };
+                ui.colored_label(color, msg);
+            }
+
+            if !self.rows.is_empty() {
+                ui.add_space(10.0);
+                self.render_results(ui);
+            }
+        });
+    }
+
+    fn render_defines(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Defines").strong());
+
+        let mut remove = None;
+        egui::Grid::new("shader_variant_defines")
+            .num_columns(4)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                for (index, define) in self.defines.iter_mut().enumerate() {
+                    ui.add(egui::TextEdit::singleline(&mut define.name).desired_width(140.0));
+                    ui.checkbox(&mut define.is_bool, "bool");
+                    ui.add_enabled(
+                        !define.is_bool,
+                        egui::TextEdit::singleline(&mut define.values)
+                            .hint_text("1, 2, 4")
+                            .desired_width(140.0),
+                    );
+                    if ui.small_button("🗑").clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove {
+            self.defines.remove(index);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("➕ Add Define").clicked() {
+                self.defines.push(DefineInput {
+                    name: format!("DEFINE_{}", self.defines.len()),
+                    is_bool: true,
+                    values: String::new(),
+                });
+            }
+            match self.collect_defines() {
+                Ok(defines) => {
+                    let count = permutation_count(&defines);
+                    let text = format!("{} permutation(s)", count);
+                    if count > MAX_PERMUTATIONS {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(220, 80, 80),
+                            format!("{} (limit {})", text, MAX_PERMUTATIONS),
+                        );
+                    } else {
+                        ui.label(text);
+                    }
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::from_rgb(220, 80, 80), e);
+                }
+            }
+        });
+    }
+
+    fn render_results(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Results").strong());
+
+        let largest = self
+            .rows
+            .iter()
+            .filter_map(|r| r.report.result.as_ref().ok().map(|s| s.spirv_bytes))
+            .max()
+            .unwrap_or(0);
+
+        egui::Grid::new("shader_variant_results")
+            .num_columns(6)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("Variant").strong());
+                ui.label(egui::RichText::new("Status").strong());
+                ui.label(egui::RichText::new("WGSL").strong());
+                ui.label(egui::RichText::new("SPIR-V").strong());
+                ui.label(egui::RichText::new("naga (ms)").strong());
+                ui.label(egui::RichText::new("Device (ms)").strong());
+                ui.end_row();
+
+                for (index, row) in self.rows.iter().enumerate() {
+                    let selected = self.selected_variant == Some(index);
+                    if ui
+                        .selectable_label(selected, row.report.permutation.label())
+                        .clicked()
+                    {
+                        self.selected_variant = if selected { None } else { Some(index) };
+                    }
+                    match &row.report.result {
+                        Ok(stats) => {
+                            ui.colored_label(egui::Color32::from_rgb(100, 200, 100), "✓");
+                            ui.label(format!("{} B", stats.wgsl_bytes));
+                            let size = format!("{} B", stats.spirv_bytes);
+                            if stats.spirv_bytes == largest {
+                                ui.colored_label(egui::Color32::from_rgb(255, 200, 80), size);
+                            } else {
+                                ui.label(size);
+                            }
+                        }
+                        Err(_) => {
+                            ui.colored_label(egui::Color32::from_rgb(220, 80, 80), "✗");
+                            ui.label("-");
+                            ui.label("-");
+                        }
+                    }
+                    ui.label(format!(
+                        "{:.2}",
+                        (row.report.compile_time + row.report.codegen_time).as_secs_f64() * 1000.0
+                    ));
+                    if let Some(error) = &row.device_error {
+                        ui.colored_label(egui::Color32::from_rgb(220, 80, 80), "✗ device")
+                            .on_hover_text(error);
+                    } else {
+                        ui.label(
+                            row.device_time
+                                .map(|t| format!("{:.2}", t.as_secs_f64() * 1000.0))
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(row) = self.selected_variant.and_then(|i| self.rows.get(i)) {
+            ui.add_space(10.0);
+            ui.label(egui::RichText::new(row.report.permutation.label()).strong());
+            if let Some(error) = &row.device_error {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 80, 80),
+                    format!("❌ Device rejected this variant: {}", error),
+                );
+            }
+            match &row.report.result {
+                Ok(stats) => {
+                    ui.label(format!(
+                        "{} entry point(s), {} helper function(s)",
+                        stats.entry_points, stats.functions
+                    ));
+                    if let Ok(wgsl) = preprocess(&self.source, &row.report.permutation) {
+                        let mut wgsl = wgsl;
+                        egui::ScrollArea::vertical()
+                            .id_salt("shader_variant_preview")
+                            .max_height(200.0)
+                            .show(ui, |ui| {
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut wgsl)
+                                        .code_editor()
+                                        .interactive(false)
+                                        .desired_width(f32::INFINITY),
+                                );
+                            });
+                    }
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_defines_are_valid() {
+        let panel = ShaderVariantsPanel::new();
+        let defines = panel.collect_defines().unwrap();
+        assert_eq!(permutation_count(&defines), 6);
+        assert_eq!(defines[1].values, DefineValues::Int(vec![1, 2, 4]));
+    }
+
+    #[test]
+    fn test_example_compiles() {
+        let mut panel = ShaderVariantsPanel::new();
+        panel.compile_all(None);
+        assert_eq!(panel.rows.len(), 6);
+        assert!(panel.rows.iter().all(|r| r.report.is_ok()));
+        assert!(panel.rows.iter().all(|r| r.device_time.is_none()));
+    }
+
+    #[test]
+    fn test_invalid_define_input() {
+        let input = DefineInput {
+            name: "COUNT".to_string(),
+            is_bool: false,
+            values: "1, two".to_string(),
+        };
+        assert!(input.to_define().is_err());
+        let input = DefineInput {
+            name: "bad name".to_string(),
+            is_bool: true,
+            values: String::new(),
+        };
+        assert!(input.to_define().is_err());
+    }
+}
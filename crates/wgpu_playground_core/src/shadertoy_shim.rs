@@ -0,0 +1,958 @@
+//! Best-effort translation of ShaderToy GLSL into WGSL
+//!
+//! Pasted ShaderToy code is rewritten token by token for the
+//! [fragment playground](crate::fragment_playground): GLSL types become
+//! WGSL ones, declarations get `var`/`let`/`const`, function signatures
+//! get WGSL syntax and `void mainImage(out vec4 fragColor, in vec2
+//! fragCoord)` becomes `fn mainImage(fragCoord: vec2<f32>) -> vec4<f32>`.
+//! A few builtins are renamed (`atan(y, x)`, `inversesqrt`, `dFdx`) and
+//! `mod` is expanded, since WGSL's `%` truncates instead of flooring.
+//!
+//! This is not a GLSL compiler. Anything it can't rewrite, like ternaries,
+//! `out` parameters, macros or texture channels, is left in place with a
+//! hint on the GLSL line that explains the WGSL way, so the compile errors
+//! that follow are easier to fix by hand.
+
+use std::collections::HashMap;
+
+/// A note on something the translation could not rewrite
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShimHint {
+    /// Line in the GLSL source, starting at 1
+    pub line: usize,
+    pub message: String,
+}
+
+/// WGSL rewritten from GLSL, with hints for what's left to do by hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    pub source: String,
+    pub hints: Vec<ShimHint>,
+}
+
+/// The shader ShaderToy starts new shaders with, to try the translation on
+pub const SHADERTOY_TEMPLATE: &str = "\
+void mainImage( out vec4 fragColor, in vec2 fragCoord )
+{
+    // Normalized pixel coordinates (from 0 to 1)
+    vec2 uv = fragCoord/iResolution.xy;
+
+    // Time varying pixel color
+    vec3 col = 0.5 + 0.5*cos(iTime+uv.xyx+vec3(0,2,4));
+
+    // Output to screen
+    fragColor = vec4(col,1.0);
+}
+";
+
+/// Whether a shader reads like GLSL rather than WGSL
+///
+/// Looks for `void`, preprocessor directives and GLSL-only types in front
+/// of a name, none of which appear in WGSL.
+pub fn looks_like_glsl(source: &str) -> bool {
+    let tokens = tokenize(source);
+    tokens
+        .iter()
+        .enumerate()
+        .any(|(i, token)| match token.kind {
+            Kind::Directive => true,
+            Kind::Ident if token.text == "void" => true,
+            Kind::Ident if token.text != "bool" && wgsl_type(token.text).is_some() => {
+                next_significant(&tokens, i + 1, tokens.len())
+                    .is_some_and(|next| tokens[next].kind == Kind::Ident)
+            }
+            _ => false,
+        })
+}
+
+/// Translate a ShaderToy GLSL shader into WGSL for the fragment playground
+pub fn translate(glsl: &str) -> Translation {
+    let mut translator = Translator::new(tokenize(glsl));
+    translator.run(0, translator.tokens.len());
+    if !translator.found_main_image {
+        translator.hint(
+            1,
+            "No `void mainImage(out vec4 fragColor, in vec2 fragCoord)` found; the playground \
+             calls `fn mainImage(fragCoord: vec2<f32>) -> vec4<f32>`",
+        );
+    }
+    translator.hints.sort_by_key(|hint| hint.line);
+    Translation {
+        source: translator.out,
+        hints: translator.hints,
+    }
+}
+
+/// WGSL spelling of a GLSL type
+fn wgsl_type(glsl: &str) -> Option<String> {
+    let vector = |prefix: &str, scalar: &str| {
+        let size = glsl.strip_prefix(prefix)?;
+        matches!(size, "2" | "3" | "4").then(|| format!("vec{}<{}>", size, scalar))
+    };
+    let matrix = || {
+        let size = glsl.strip_prefix("mat")?;
+        let (columns, rows) = size.split_once('x').unwrap_or((size, size));
+        let valid = |n: &str| matches!(n, "2" | "3" | "4");
+        (valid(columns) && valid(rows)).then(|| format!("mat{}x{}<f32>", columns, rows))
+    };
+    match glsl {
+        "float" => Some("f32".to_string()),
+        "int" => Some("i32".to_string()),
+        "uint" => Some("u32".to_string()),
+        "bool" => Some("bool".to_string()),
+        _ => vector("vec", "f32")
+            .or_else(|| vector("ivec", "i32"))
+            .or_else(|| vector("uvec", "u32"))
+            .or_else(|| vector("bvec", "bool"))
+            .or_else(matrix),
+    }
+}
+
+/// Builtins that only changed their name
+fn renamed_builtin(glsl: &str) -> Option<&'static str> {
+    Some(match glsl {
+        "inversesqrt" => "inverseSqrt",
+        "dFdx" => "dpdx",
+        "dFdy" => "dpdy",
+        "iGlobalTime" => "iTime",
+        _ => return None,
+    })
+}
+
+/// Words that can follow a type without being a declared name
+fn is_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "in" | "out" | "inout" | "const" | "return" | "if" | "else" | "for" | "while"
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Ident,
+    Number,
+    Punct,
+    Space,
+    Comment,
+    /// A `#` line
+    Directive,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    line: usize,
+}
+
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut at = 0;
+    // Only whitespace so far on the current line
+    let mut line_start = true;
+    while at < source.len() {
+        let rest = &source[at..];
+        let c = rest.chars().next().unwrap();
+        let until = |end: Option<usize>| end.unwrap_or(rest.len());
+        let (kind, len) = if c == '#' && line_start {
+            (Kind::Directive, until(rest.find('\n')))
+        } else if rest.starts_with("//") {
+            (Kind::Comment, until(rest.find('\n')))
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            (Kind::Comment, until(comment.find("*/").map(|end| end + 4)))
+        } else if c.is_whitespace() {
+            (Kind::Space, until(rest.find(|c: char| !c.is_whitespace())))
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'));
+            (Kind::Ident, until(end))
+        } else if c.is_ascii_digit()
+            || (c == '.' && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            (Kind::Number, number_len(rest))
+        } else {
+            let two = rest.get(..2).unwrap_or("");
+            let len = match two {
+                "++" | "--" | "+=" | "-=" | "*=" | "/=" | "%=" | "==" | "!=" | "<=" | ">="
+                | "&&" | "||" | "<<" | ">>" => 2,
+                _ => c.len_utf8(),
+            };
+            (Kind::Punct, len)
+        };
+        let text = &rest[..len];
+        tokens.push(Token { kind, text, line });
+        line += text.matches('\n').count();
+        line_start = match kind {
+            Kind::Space => line_start || text.contains('\n'),
+            Kind::Comment => line_start,
+            _ => false,
+        };
+        at += len;
+    }
+    tokens
+}
+
+/// Length of the number literal `text` starts with, exponent and suffix
+/// included
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let c = bytes[len];
+        let exponent_sign = (c == b'+' || c == b'-')
+            && len > 0
+            && matches!(bytes[len - 1], b'e' | b'E')
+            && !text.starts_with("0x");
+        if c.is_ascii_alphanumeric() || c == b'.' || exponent_sign {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+/// Index of the first token from `from` that isn't whitespace or a comment
+fn next_significant(tokens: &[Token], from: usize, end: usize) -> Option<usize> {
+    (from..end).find(|&i| !matches!(tokens[i].kind, Kind::Space | Kind::Comment))
+}
+
+struct Translator<'a> {
+    tokens: Vec<Token<'a>>,
+    out: String,
+    hints: Vec<ShimHint>,
+    /// Brace nesting at the current token
+    depth: usize,
+    /// Text written instead of a token, by token index
+    replacements: HashMap<usize, String>,
+    /// Text written after the next `{`
+    after_brace: Option<String>,
+    /// Color output of a `mainImage` whose body starts at the next `{`
+    pending_main_image: Option<String>,
+    /// Brace depth of the `mainImage` body and its color output
+    main_image: Option<(usize, String)>,
+    found_main_image: bool,
+    /// A `struct` keyword was seen and its body starts at the next `{`
+    pending_struct: bool,
+    /// Brace depth of the struct body being translated
+    struct_depth: Option<usize>,
+    /// Names of the structs declared so far, usable as types
+    struct_names: Vec<&'a str>,
+}
+
+impl<'a> Translator<'a> {
+    fn new(tokens: Vec<Token<'a>>) -> Self {
+        Self {
+            tokens,
+            out: String::new(),
+            hints: Vec::new(),
+            depth: 0,
+            replacements: HashMap::new(),
+            after_brace: None,
+            pending_main_image: None,
+            main_image: None,
+            found_main_image: false,
+            pending_struct: false,
+            struct_depth: None,
+            struct_names: Vec::new(),
+        }
+    }
+
+    fn hint(&mut self, line: usize, message: &str) {
+        let hint = ShimHint {
+            line,
+            message: message.to_string(),
+        };
+        if !self.hints.contains(&hint) {
+            self.hints.push(hint);
+        }
+    }
+
+    fn next(&self, from: usize, end: usize) -> Option<usize> {
+        next_significant(&self.tokens, from, end)
+    }
+
+    fn text_at(&self, index: Option<usize>) -> Option<&'a str> {
+        index.map(|i| self.tokens[i].text)
+    }
+
+    /// Index of the `)` closing the `(` at `open`
+    fn closing_paren(&self, open: usize, end: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        for i in open..end {
+            match (self.tokens[i].kind, self.tokens[i].text) {
+                (Kind::Punct, "(") => depth += 1,
+                (Kind::Punct, ")") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Token ranges of the comma separated arguments between `open` and
+    /// `close`
+    fn arguments(&self, open: usize, close: usize) -> Vec<(usize, usize)> {
+        let mut arguments = Vec::new();
+        let mut depth = 0usize;
+        let mut start = open + 1;
+        for i in open + 1..close {
+            match self.tokens[i].text {
+                "(" | "[" => depth += 1,
+                ")" | "]" => depth = depth.saturating_sub(1),
+                "," if depth == 0 => {
+                    arguments.push((start, i));
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if self.next(start, close).is_some() {
+            arguments.push((start, close));
+        }
+        arguments
+    }
+
+    /// Source text of a token range as written
+    fn raw(&self, start: usize, end: usize) -> String {
+        self.tokens[start..end].iter().map(|t| t.text).collect()
+    }
+
+    /// Translate a token range on its own and return the WGSL
+    fn expression(&mut self, start: usize, end: usize) -> String {
+        let out = std::mem::take(&mut self.out);
+        self.run(start, end);
+        std::mem::replace(&mut self.out, out).trim().to_string()
+    }
+
+    fn run(&mut self, mut i: usize, end: usize) {
+        while i < end {
+            i = self.step(i, end);
+        }
+    }
+
+    /// Translate the token at `i` and return the index of the next one
+    fn step(&mut self, i: usize, end: usize) -> usize {
+        if let Some(replacement) = self.replacements.remove(&i) {
+            self.out.push_str(&replacement);
+            return i + 1;
+        }
+        let token = self.tokens[i];
+        match token.kind {
+            Kind::Directive => self.directive(i),
+            Kind::Punct => self.punct(i, end),
+            Kind::Ident => self.ident(i, end),
+            Kind::Number | Kind::Space | Kind::Comment => {
+                self.out.push_str(token.text);
+                i + 1
+            }
+        }
+    }
+
+    fn directive(&mut self, i: usize) -> usize {
+        let token = self.tokens[i];
+        let body = token.text[1..].trim();
+        let (name, value) = body
+            .strip_prefix("define")
+            .map(|rest| {
+                let rest = rest.trim_start();
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            })
+            .unwrap_or(("", ""));
+        if !name.is_empty() && !value.starts_with('(') && !value.trim().is_empty() {
+            let mut value_translator = Translator::new(tokenize(value.trim()));
+            value_translator.depth = 1;
+            value_translator.run(0, value_translator.tokens.len());
+            self.out
+                .push_str(&format!("const {} = {};", name, value_translator.out));
+            for hint in value_translator.hints {
+                self.hint(token.line, &hint.message);
+            }
+            return i + 1;
+        }
+        self.out.push_str("// ");
+        self.out.push_str(token.text);
+        let message = if name.is_empty() {
+            "WGSL has no preprocessor; keep the branch you need and delete the directives"
+        } else {
+            "Macros with parameters become WGSL functions; calls stay the same once the \
+             parameters have types"
+        };
+        self.hint(token.line, message);
+        i + 1
+    }
+
+    fn punct(&mut self, i: usize, end: usize) -> usize {
+        let token = self.tokens[i];
+        match token.text {
+            "{" => {
+                self.depth += 1;
+                self.out.push('{');
+                if let Some(text) = self.after_brace.take() {
+                    self.out.push_str(&text);
+                }
+                if let Some(color) = self.pending_main_image.take() {
+                    self.main_image = Some((self.depth, color));
+                }
+                if std::mem::take(&mut self.pending_struct) {
+                    self.struct_depth = Some(self.depth);
+                }
+            }
+            "}" => {
+                if let Some((depth, color)) = &self.main_image {
+                    if *depth == self.depth {
+                        self.out.push_str(&format!("    return {};\n", color));
+                        self.main_image = None;
+                    }
+                }
+                self.out.push('}');
+                let closes_struct = self.struct_depth == Some(self.depth);
+                self.depth = self.depth.saturating_sub(1);
+                if closes_struct {
+                    self.struct_depth = None;
+                    // WGSL structs end without a semicolon
+                    let next = self.next(i + 1, end);
+                    if let Some(next) = next.filter(|&n| self.tokens[n].text == ";") {
+                        return next + 1;
+                    }
+                }
+            }
+            "?" => {
+                self.out.push('?');
+                self.hint(
+                    token.line,
+                    "WGSL has no `c ? a : b`; write `select(b, a, c)` (both sides are \
+                     evaluated) or an `if`",
+                );
+            }
+            text => self.out.push_str(text),
+        }
+        i + 1
+    }
+
+    fn ident(&mut self, i: usize, end: usize) -> usize {
+        let token = self.tokens[i];
+        let next = self.next(i + 1, end);
+        let next_text = self.text_at(next);
+        let calls = next_text == Some("(");
+        match token.text {
+            "const" => {
+                let ty = next.filter(|&n| self.is_type(self.tokens[n].text));
+                let name = ty.and_then(|ty| self.next(ty + 1, end));
+                if let (Some(ty), Some(name)) = (ty, name) {
+                    if self.tokens[name].kind == Kind::Ident {
+                        return self.declaration(ty, name, end, true);
+                    }
+                }
+            }
+            "if" | "for" | "while" if calls => {
+                if let Some(close) = self.closing_paren(next.unwrap(), end) {
+                    if self.brace_body(close, end) {
+                        self.replacements.insert(close, ") {".to_string());
+                    }
+                }
+            }
+            "else" if !matches!(next_text, Some("{") | Some("if")) => {
+                if self.brace_body(i, end) {
+                    self.out.push_str("else {");
+                    return i + 1;
+                }
+            }
+            "struct" => {
+                self.pending_struct = true;
+                if let Some(name) = next.filter(|&n| self.tokens[n].kind == Kind::Ident) {
+                    self.struct_names.push(self.tokens[name].text);
+                }
+            }
+            "precision" => {
+                // `precision mediump float;` has no WGSL counterpart
+                let semicolon = (i..end).find(|&n| self.tokens[n].text == ";");
+                let stop = semicolon.map_or(end, |n| n + 1);
+                self.out.push_str(&format!("// {}", self.raw(i, stop)));
+                return stop;
+            }
+            "return" if self.main_image.is_some() && next_text == Some(";") => {
+                let color = &self.main_image.as_ref().unwrap().1;
+                self.out.push_str(&format!("return {}", color));
+                return i + 1;
+            }
+            "mod" if calls => return self.modulo(i, next.unwrap(), end),
+            "atan" if calls => {
+                let arguments = self
+                    .closing_paren(next.unwrap(), end)
+                    .map(|close| self.arguments(next.unwrap(), close).len());
+                let name = if arguments == Some(2) {
+                    "atan2"
+                } else {
+                    "atan"
+                };
+                self.out.push_str(name);
+                return i + 1;
+            }
+            "texture" | "textureLod" | "texelFetch" | "textureSize" if calls => {
+                self.hint(
+                    token.line,
+                    "Texture channels aren't available in the playground; replace the lookup \
+                     with a procedural function",
+                );
+            }
+            "lessThan" | "lessThanEqual" | "greaterThan" | "greaterThanEqual" | "equal"
+            | "notEqual"
+                if calls =>
+            {
+                self.hint(
+                    token.line,
+                    "Vector comparisons are operators in WGSL: `a < b` on vectors gives a \
+                     vector of bools",
+                );
+            }
+            text if text.starts_with("iChannel") || text == "iDate" || text == "iSampleRate" => {
+                self.hint(
+                    token.line,
+                    "Only iResolution, iTime, iTimeDelta, iFrame and iMouse are provided, plus \
+                     iAudio, iBeat and audioBand(i) for sound input",
+                );
+            }
+            "void" => return self.typed(i, end),
+            text if self.is_type(text) => return self.typed(i, end),
+            text => {
+                if let Some(renamed) = renamed_builtin(text) {
+                    self.out.push_str(renamed);
+                    return i + 1;
+                }
+            }
+        }
+        self.out.push_str(token.text);
+        i + 1
+    }
+
+    /// Close the single statement after `head` with a `}` when it has
+    /// no braces, which WGSL requires; the caller opens it
+    fn brace_body(&mut self, head: usize, end: usize) -> bool {
+        let Some(body) = self.next(head + 1, end) else {
+            return false;
+        };
+        if self.tokens[body].text == "{" {
+            return false;
+        }
+        let Some(semicolon) = self.statement_end(body, end) else {
+            self.hint(
+                self.tokens[head].line,
+                "WGSL needs braces around every `if`, `else` and loop body",
+            );
+            return false;
+        };
+        // Statements that end several bodies at once
+        let closing = self
+            .replacements
+            .remove(&semicolon)
+            .unwrap_or_else(|| ";".to_string());
+        self.replacements
+            .insert(semicolon, format!("{} }}", closing));
+        true
+    }
+
+    /// Index of the `;` ending the statement at `start`, if no braces are
+    /// in the way
+    fn statement_end(&self, start: usize, end: usize) -> Option<usize> {
+        let text = self.tokens[start].text;
+        if matches!(text, "if" | "for" | "while") {
+            let open = self.next(start + 1, end)?;
+            let close = self.closing_paren(open, end)?;
+            let semicolon = self.statement_end(self.next(close + 1, end)?, end)?;
+            let after = self.next(semicolon + 1, end);
+            if text == "if" && self.text_at(after) == Some("else") {
+                return self.statement_end(self.next(after? + 1, end)?, end);
+            }
+            return Some(semicolon);
+        }
+        let mut depth = 0usize;
+        for n in start..end {
+            match self.tokens[n].text {
+                "(" => depth += 1,
+                ")" => depth = depth.saturating_sub(1),
+                "{" | "}" => return None,
+                ";" if depth == 0 => return Some(n),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn is_type(&self, text: &str) -> bool {
+        wgsl_type(text).is_some() || self.struct_names.contains(&text)
+    }
+
+    /// WGSL spelling of a type name, built-in or a declared struct
+    fn type_name(&self, text: &str) -> String {
+        wgsl_type(text).unwrap_or_else(|| text.to_string())
+    }
+
+    /// Reads `[N]` at `open`, returning the length and the index after `]`
+    fn array_suffix(&self, open: Option<usize>, end: usize) -> Option<(String, usize)> {
+        let open = open.filter(|&o| self.tokens[o].text == "[")?;
+        let next = self.next(open + 1, end)?;
+        if self.tokens[next].text == "]" {
+            return Some((String::new(), next + 1));
+        }
+        let close = self.next(next + 1, end)?;
+        (self.tokens[close].text == "]").then(|| (self.tokens[next].text.to_string(), close + 1))
+    }
+
+    /// A type name: a constructor, a declaration or a function definition
+    fn typed(&mut self, i: usize, end: usize) -> usize {
+        let text = self.tokens[i].text;
+        let next = self.next(i + 1, end);
+
+        // `float[3](...)` array constructors
+        if let Some((length, after)) = self.array_suffix(next, end) {
+            let element = self.type_name(text);
+            if length.is_empty() {
+                self.out.push_str("array");
+            } else {
+                self.out
+                    .push_str(&format!("array<{}, {}>", element, length));
+            }
+            return after;
+        }
+
+        if let Some(name) =
+            next.filter(|&n| self.tokens[n].kind == Kind::Ident && !is_keyword(self.tokens[n].text))
+        {
+            let after = self.next(name + 1, end);
+            if self.depth == 0 && self.text_at(after) == Some("(") {
+                return self.function(i, name, after.unwrap(), end);
+            }
+            return self.declaration(i, name, end, false);
+        }
+
+        self.out.push_str(&self.type_name(text));
+        i + 1
+    }
+
+    /// `TYPE name = ...` as a WGSL variable, constant or struct member
+    fn declaration(&mut self, ty: usize, name: usize, end: usize, constant: bool) -> usize {
+        let mut ty_name = self.type_name(self.tokens[ty].text);
+        let mut resume = name + 1;
+        if let Some((length, after)) = self.array_suffix(self.next(name + 1, end), end) {
+            ty_name = format!("array<{}, {}>", ty_name, length);
+            resume = after;
+        }
+        let name_text = self.tokens[name].text;
+
+        if self.struct_depth == Some(self.depth) {
+            // Struct members are `name: type,`
+            self.out.push_str(&format!("{}: {},", name_text, ty_name));
+            let semicolon = self.next(resume, end);
+            return match semicolon.filter(|&s| self.tokens[s].text == ";") {
+                Some(s) => s + 1,
+                None => resume,
+            };
+        }
+
+        let keyword = match (self.depth, constant) {
+            (0, true) => "const",
+            (0, false) => "var<private>",
+            (_, true) => "let",
+            (_, false) => "var",
+        };
+        self.out
+            .push_str(&format!("{} {}: {}", keyword, name_text, ty_name));
+
+        // `float a = 1.0, b;` is split into one declaration per name
+        let mut depth = 0usize;
+        for n in resume..end {
+            match self.tokens[n].text {
+                "(" | "[" => depth += 1,
+                ")" | "]" => depth = depth.saturating_sub(1),
+                ";" if depth == 0 => break,
+                "," if depth == 0 => {
+                    let Some(next_name) = self.next(n + 1, end) else {
+                        break;
+                    };
+                    let mut next_ty = self.type_name(self.tokens[ty].text);
+                    let suffix = self.array_suffix(self.next(next_name + 1, end), end);
+                    if let Some((length, after)) = suffix {
+                        next_ty = format!("array<{}, {}>", next_ty, length);
+                        for skipped in next_name + 1..after {
+                            self.replacements.insert(skipped, String::new());
+                        }
+                    }
+                    for skipped in n + 1..next_name {
+                        self.replacements.insert(skipped, String::new());
+                    }
+                    self.replacements.insert(n, format!("; {} ", keyword));
+                    self.replacements.insert(
+                        next_name,
+                        format!("{}: {}", self.tokens[next_name].text, next_ty),
+                    );
+                }
+                _ => {}
+            }
+        }
+        resume
+    }
+
+    /// A function definition or prototype at the top level
+    fn function(&mut self, ty: usize, name: usize, open: usize, end: usize) -> usize {
+        let line = self.tokens[name].line;
+        let Some(close) = self.closing_paren(open, end) else {
+            self.out.push_str(&self.raw(ty, open));
+            return open;
+        };
+        let after = self.next(close + 1, end);
+        if self.text_at(after) == Some(";") {
+            let after = after.unwrap();
+            self.out.push_str(&format!(
+                "// {}",
+                self.raw(ty, after + 1).replace('\n', " ")
+            ));
+            self.hint(
+                line,
+                "WGSL functions can be used before their definition, so prototypes are not \
+                 needed",
+            );
+            return after + 1;
+        }
+
+        // Qualifiers, type and name of every parameter
+        let mut parameters = Vec::new();
+        for (start, stop) in self.arguments(open, close) {
+            let words: Vec<&str> = (start..stop)
+                .filter(|&n| self.tokens[n].kind == Kind::Ident)
+                .map(|n| self.tokens[n].text)
+                .collect();
+            let qualifier = words
+                .iter()
+                .find(|w| matches!(**w, "out" | "inout"))
+                .copied();
+            let typed: Vec<&str> = words
+                .into_iter()
+                .filter(|w| {
+                    !matches!(
+                        *w,
+                        "in" | "out" | "inout" | "const" | "highp" | "mediump" | "lowp"
+                    )
+                })
+                .collect();
+            // `void f(void)`
+            if typed == ["void"] {
+                continue;
+            }
+            if let [param_ty, param_name, ..] = typed[..] {
+                parameters.push((qualifier, self.type_name(param_ty), param_name));
+            }
+        }
+
+        let function_name = self.tokens[name].text;
+        if function_name == "mainImage" {
+            let color = parameters
+                .iter()
+                .find(|(qualifier, ty, _)| qualifier.is_some() && ty == "vec4<f32>");
+            let coord = parameters
+                .iter()
+                .find(|(qualifier, ty, _)| qualifier.is_none() && ty == "vec2<f32>");
+            if let (Some(&(_, _, color)), Some(&(_, _, coord))) = (color, coord) {
+                self.found_main_image = true;
+                self.out
+                    .push_str(&format!("fn mainImage({}: vec2<f32>) -> vec4<f32>", coord));
+                self.after_brace = Some(format!("\n    var {}: vec4<f32>;", color));
+                self.pending_main_image = Some(color.to_string());
+                return close + 1;
+            }
+        }
+
+        let mut signature = Vec::new();
+        for (qualifier, param_ty, param_name) in parameters {
+            if qualifier.is_some() {
+                self.hint(
+                    line,
+                    "`out` and `inout` parameters are pointers in WGSL: write `*name` in the \
+                     body and pass `&variable` when calling",
+                );
+                signature.push(format!("{}: ptr<function, {}>", param_name, param_ty));
+            } else {
+                signature.push(format!("{}: {}", param_name, param_ty));
+            }
+        }
+        self.out
+            .push_str(&format!("fn {}({})", function_name, signature.join(", ")));
+        let return_type = self.tokens[ty].text;
+        if return_type != "void" {
+            self.out
+                .push_str(&format!(" -> {}", self.type_name(return_type)));
+        }
+        close + 1
+    }
+
+    /// `mod(x, y)` expanded to the flooring remainder GLSL computes
+    fn modulo(&mut self, i: usize, open: usize, end: usize) -> usize {
+        let Some(close) = self.closing_paren(open, end) else {
+            self.out.push_str("mod");
+            return i + 1;
+        };
+        let arguments = self.arguments(open, close);
+        let [(x_start, x_end), (y_start, y_end)] = arguments[..] else {
+            self.out.push_str("mod");
+            return i + 1;
+        };
+        let x = self.expression(x_start, x_end);
+        let y = self.expression(y_start, y_end);
+        self.out.push_str(&format!(
+            "(({x}) - ({y}) * floor(({x}) / ({y})))",
+            x = x,
+            y = y
+        ));
+        close + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(translation: &Translation) -> Vec<(usize, &str)> {
+        translation
+            .hints
+            .iter()
+            .map(|hint| (hint.line, hint.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_translates_the_template() {
+        let translation = translate(SHADERTOY_TEMPLATE);
+        assert!(translation.hints.is_empty(), "{:?}", translation.hints);
+        let source = &translation.source;
+        assert!(source.starts_with("fn mainImage(fragCoord: vec2<f32>) -> vec4<f32>\n{"));
+        assert!(source.contains("var fragColor: vec4<f32>;"));
+        assert!(source.contains("var uv: vec2<f32> = fragCoord/iResolution.xy;"));
+        assert!(source.contains("vec3<f32>(0,2,4)"));
+        assert!(source.contains("fragColor = vec4<f32>(col,1.0);"));
+        assert!(source.ends_with("    return fragColor;\n}\n"));
+        // Comments and line breaks stay where they were
+        assert_eq!(
+            source.lines().count(),
+            SHADERTOY_TEMPLATE.lines().count() + 2
+        );
+    }
+
+    #[test]
+    fn test_declarations() {
+        let translation = translate(
+            "const float PI = 3.14159;\n\
+             vec3 light;\n\
+             float f() { const int n = 4; float a = 1.0, b[2]; return a; }",
+        );
+        let source = &translation.source;
+        assert!(source.contains("const PI: f32 = 3.14159;"));
+        assert!(source.contains("var<private> light: vec3<f32>;"));
+        assert!(source.contains("fn f() -> f32 {"));
+        assert!(source.contains("let n: i32 = 4;"));
+        assert!(source.contains("var a: f32 = 1.0; var b: array<f32, 2>;"));
+    }
+
+    #[test]
+    fn test_functions_and_structs() {
+        let translation = translate(
+            "struct Ray { vec3 origin; vec3 dir; };\n\
+             float sdSphere(vec3 p, float r);\n\
+             float sdSphere(in vec3 p, float r) { return length(p) - r; }\n\
+             void bounce(inout Ray ray) {}\n\
+             Ray camera() { return Ray(vec3(0.0), vec3(0.0, 0.0, 1.0)); }",
+        );
+        let source = &translation.source;
+        assert!(source.contains("struct Ray { origin: vec3<f32>, dir: vec3<f32>, }\n"));
+        assert!(source.contains("// float sdSphere(vec3 p, float r);"));
+        assert!(source.contains("fn sdSphere(p: vec3<f32>, r: f32) -> f32 {"));
+        assert!(source.contains("fn bounce(ray: ptr<function, Ray>) {}"));
+        assert!(source.contains("fn camera() -> Ray {"));
+        let hints = messages(&translation);
+        // The prototype, the pointer and the missing `mainImage`
+        assert_eq!(hints.len(), 3);
+        assert!(hints[0].1.contains("mainImage"));
+        assert_eq!(hints[1].0, 2);
+        assert_eq!(hints[2].0, 4);
+        assert!(hints[2].1.contains("pointers"));
+    }
+
+    #[test]
+    fn test_builtins() {
+        let translation = translate(
+            "#define PI 3.14159\n\
+             #define SCALE vec2(2.0)\n\
+             #define SQ(x) ((x)*(x))\n\
+             float f(vec2 p) {\n\
+                 float a = atan(p.y, p.x) + atan(p.x);\n\
+                 float m = mod(float(iFrame), 2.0 * PI);\n\
+                 return inversesqrt(a) + dFdx(m);\n\
+             }",
+        );
+        let source = &translation.source;
+        assert!(source.contains("const PI = 3.14159;"));
+        assert!(source.contains("const SCALE = vec2<f32>(2.0);"));
+        assert!(source.contains("// #define SQ(x)"));
+        assert!(source.contains("atan2(p.y, p.x) + atan(p.x)"));
+        assert!(source.contains("((f32(iFrame)) - (2.0 * PI) * floor((f32(iFrame)) / (2.0 * PI)))"));
+        assert!(source.contains("inverseSqrt(a) + dpdx(m)"));
+        assert_eq!(messages(&translation).len(), 2);
+    }
+
+    #[test]
+    fn test_bodies_get_braces() {
+        let source = translate(
+            "float f(float x) {\n\
+                 for (int i = 0; i < 4; i++) if (x > 1.0) x *= 0.5; else x += f2(x, 1.0);\n\
+                 while (x > 2.0) { x -= 1.0; }\n\
+                 return x;\n\
+             }",
+        )
+        .source;
+        assert!(source.contains(
+            "for (var i: i32 = 0; i < 4; i++) { if (x > 1.0) { x *= 0.5; } else { x += f2(x, 1.0); } }"
+        ));
+        assert!(source.contains("while (x > 2.0) { x -= 1.0; }"));
+    }
+
+    #[test]
+    fn test_hints_for_what_stays() {
+        let translation = translate(
+            "void mainImage(out vec4 c, in vec2 p) {\n\
+                 vec4 t = texture(iChannel0, p);\n\
+                 c = p.x > 0.5 ? t : vec4(1.0);\n\
+             }",
+        );
+        let lines: Vec<usize> = messages(&translation).iter().map(|h| h.0).collect();
+        assert_eq!(lines, [2, 2, 3]);
+        assert!(translation.source.contains("    return c;\n}"));
+    }
+
+    #[test]
+    fn test_missing_main_image() {
+        let hints = translate("float f() { return 1.0; }").hints;
+        assert_eq!(hints.len(), 1);
+        assert!(hints[0].message.contains("mainImage"));
+    }
+
+    #[test]
+    fn test_looks_like_glsl() {
+        assert!(looks_like_glsl(SHADERTOY_TEMPLATE));
+        assert!(looks_like_glsl("#define PI 3.14"));
+        assert!(looks_like_glsl(
+            "float f() { vec3 a = vec3(1.0); return a.x; }"
+        ));
+        assert!(!looks_like_glsl(
+            "fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {\n\
+                 let on: bool = true;\n\
+                 return vec4<f32>(vec3(0.5), 1.0);\n\
+             }"
+        ));
+    }
+}
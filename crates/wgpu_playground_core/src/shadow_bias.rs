@@ -0,0 +1,849 @@
+//! Depth bias, shadow acne and peter-panning
+//!
+//! A directional light casts shadows through a shadow map: a depth-only
+//! pass renders the scene from the light, and the main pass compares the
+//! depth of every fragment as seen from the light with the depth stored in
+//! the map. A map texel covers a patch of surface, and a surface tilted
+//! away from the light spans a range of depths within that patch. The
+//! depth stored at the texel center then lies in front of part of the
+//! patch, which shadows itself in stripes: shadow acne.
+//!
+//! The depth bias of the shadow pipeline's depth-stencil state pushes the
+//! stored depths away from the light: `constant` by a number of the
+//! smallest depth steps of the format, `slope_scale` by a multiple of how
+//! fast the triangle's depth changes across a texel, the total limited by
+//! `clamp`. Too little bias leaves acne; too much and shadows detach from
+//! the thin objects casting them, so these seem to float: peter-panning.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{look_at, mat4_mul, orthographic, rotation_z, transform_point, Mat4};
+use crate::operation_errors::OperationErrors;
+use crate::render_pipeline::{CullMode, DepthStencilState};
+use crate::upload_belt;
+use crate::water::{add_box, SceneVertex};
+use bytemuck::Zeroable;
+
+/// Side of the square ground
+pub const GROUND_SIZE: f32 = 16.0;
+
+/// Radius of a sphere around the origin holding the whole scene
+pub const SCENE_RADIUS: f32 = 12.0;
+
+/// Distance of the light's view from the origin
+pub const LIGHT_DISTANCE: f32 = 20.0;
+
+/// Side lengths the shadow map can be created with, in texels
+pub const SHADOW_MAP_SIZES: [u32; 4] = [256, 512, 1024, 2048];
+
+/// Shadow map formats to choose from
+pub const SHADOW_FORMATS: [wgpu::TextureFormat; 2] = [
+    wgpu::TextureFormat::Depth16Unorm,
+    wgpu::TextureFormat::Depth32Float,
+];
+
+/// Operation name of building the shadow pipeline in [`OperationErrors`]
+pub const SHADOW_PIPELINE: &str = "shadow_pipeline";
+
+/// Bias settings to start from: name, constant bias as a distance along
+/// the light direction, slope scale and clamp
+///
+/// The constant is a distance because its unit depends on the format; see
+/// [`constant_for_distance`].
+pub const BIAS_PRESETS: [(&str, f32, f32, f32); 3] = [
+    ("No bias", 0.0, 0.0, 0.0),
+    ("Balanced", 0.002, 2.0, 0.0),
+    ("Too much", 1.0, 2.0, 0.0),
+];
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub const SHADOW_SHADER: &str = r#"// Shadow Acne Example
+//
+// vs_shadow renders the scene's depth from the light into the shadow map,
+// with the depth bias of its pipeline. fs_scene looks each fragment up in
+// the map and compares its own depth from the light with the stored one.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    light_view_proj: mat4x4<f32>,
+    // Unit vector towards the light
+    light_dir: vec3<f32>,
+    view_mode: u32,
+};
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(1) @binding(0) var shadow_map: texture_depth_2d;
+@group(1) @binding(1) var shadow_sampler: sampler_comparison;
+
+const AMBIENT: f32 = 0.25;
+
+@vertex
+fn vs_shadow(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    return uniforms.light_view_proj * vec4<f32>(position, 1.0);
+}
+
+struct SceneInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+struct SceneOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+@vertex
+fn vs_scene(in: SceneInput) -> SceneOutput {
+    var out: SceneOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(in.position, 1.0);
+    out.world = in.position;
+    out.normal = in.normal;
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_scene(in: SceneOutput) -> @location(0) vec4<f32> {
+    // The light's projection is orthographic, so w is 1
+    let light_clip = uniforms.light_view_proj * vec4<f32>(in.world, 1.0);
+    let uv = vec2<f32>(light_clip.x * 0.5 + 0.5, 0.5 - light_clip.y * 0.5);
+    // 1 where the fragment is no farther from the light than the map says
+    let lit = textureSampleCompare(shadow_map, shadow_sampler, uv, light_clip.z);
+
+    let diffuse = max(dot(normalize(in.normal), uniforms.light_dir), 0.0);
+    let shaded = in.color * (AMBIENT + (1.0 - AMBIENT) * diffuse * lit);
+    switch uniforms.view_mode {
+        case 1u: {
+            return vec4<f32>(vec3<f32>(lit), 1.0);
+        }
+        case 2u: {
+            // Alternate the brightness of neighbouring shadow map texels
+            let texel = floor(uv * vec2<f32>(textureDimensions(shadow_map)));
+            let checker = f32((i32(texel.x) + i32(texel.y)) & 1);
+            return vec4<f32>(shaded * (0.7 + 0.3 * checker), 1.0);
+        }
+        default: {
+            return vec4<f32>(shaded, 1.0);
+        }
+    }
+}
+"#;
+
+/// What the main pass shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowView {
+    /// The lit scene
+    Lit,
+    /// The result of the shadow map comparison alone
+    ShadowTerm,
+    /// The lit scene with the shadow map texels in a checkerboard
+    Texels,
+}
+
+impl ShadowView {
+    pub const ALL: [ShadowView; 3] = [ShadowView::Lit, ShadowView::ShadowTerm, ShadowView::Texels];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShadowView::Lit => "Lit",
+            ShadowView::ShadowTerm => "Shadow term",
+            ShadowView::Texels => "Map texels",
+        }
+    }
+
+    fn index(&self) -> u32 {
+        match self {
+            ShadowView::Lit => 0,
+            ShadowView::ShadowTerm => 1,
+            ShadowView::Texels => 2,
+        }
+    }
+}
+
+/// Unit vector towards a light at `azimuth` around the vertical axis and
+/// `elevation` above the ground, both in degrees
+pub fn light_direction(azimuth: f32, elevation: f32) -> [f32; 3] {
+    let (azimuth, elevation) = (azimuth.to_radians(), elevation.to_radians());
+    [
+        elevation.cos() * azimuth.cos(),
+        elevation.sin(),
+        elevation.cos() * azimuth.sin(),
+    ]
+}
+
+/// Orthographic view-projection of a light shining from `direction`,
+/// covering the whole scene
+pub fn light_view_proj(direction: [f32; 3]) -> Mat4 {
+    let eye = direction.map(|c| c * LIGHT_DISTANCE);
+    let view = look_at(eye, [0.0; 3], [0.0, 1.0, 0.0]);
+    let projection = orthographic(
+        SCENE_RADIUS,
+        SCENE_RADIUS,
+        LIGHT_DISTANCE - SCENE_RADIUS,
+        LIGHT_DISTANCE + SCENE_RADIUS,
+    );
+    mat4_mul(&projection, &view)
+}
+
+/// Distance along the light direction of one step of constant depth bias
+/// in a shadow map of `format`
+///
+/// Unorm formats step evenly through the light's depth range. Float steps
+/// grow with the depth; this is the step from the middle of the range up,
+/// where most of the scene lies.
+pub fn constant_bias_unit(format: wgpu::TextureFormat) -> f32 {
+    let step = match format {
+        wgpu::TextureFormat::Depth16Unorm => 1.0 / 65535.0,
+        wgpu::TextureFormat::Depth24Plus | wgpu::TextureFormat::Depth24PlusStencil8 => {
+            1.0 / 16_777_215.0
+        }
+        // 2^(e - 23) for depths in [2^e, 2^(e + 1)), with e = -1
+        _ => 2.0f32.powi(-24),
+    };
+    step * 2.0 * SCENE_RADIUS
+}
+
+/// Constant depth bias moving the stored depths `distance` away from the
+/// light in a shadow map of `format`
+pub fn constant_for_distance(format: wgpu::TextureFormat, distance: f32) -> i32 {
+    (distance / constant_bias_unit(format)).round() as i32
+}
+
+/// The ground with a cube, a thin wall and a ramp tilted towards the
+/// default light direction
+pub fn scene_mesh() -> (Vec<SceneVertex>, Vec<u16>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let half = GROUND_SIZE / 2.0;
+
+    add_box(
+        &mut vertices,
+        &mut indices,
+        [-half, -0.1, -half],
+        [half, 0.0, half],
+        [0.8, 0.8, 0.78],
+    );
+    add_box(
+        &mut vertices,
+        &mut indices,
+        [-3.0, 0.0, -1.0],
+        [-1.0, 2.0, 1.0],
+        [0.85, 0.3, 0.25],
+    );
+    // Thin enough that a large bias pushes its shadow off its base
+    add_box(
+        &mut vertices,
+        &mut indices,
+        [1.5, 0.0, -3.0],
+        [1.6, 2.5, 3.0],
+        [0.3, 0.55, 0.85],
+    );
+
+    // A slab rotated about its lower edge to face the light
+    let ramp_start = vertices.len();
+    add_box(
+        &mut vertices,
+        &mut indices,
+        [-5.0, 0.0, 4.0],
+        [0.0, 0.2, 6.5],
+        [0.9, 0.7, 0.25],
+    );
+    let tilt = rotation_z(-0.35);
+    for vertex in &mut vertices[ramp_start..] {
+        let [x, y, z] = transform_point(&tilt, vertex.position);
+        vertex.position = [x - 0.5, y, z];
+        vertex.normal = transform_point(&tilt, vertex.normal);
+    }
+    (vertices, indices)
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: Mat4,
+    light_view_proj: Mat4,
+    light_dir: [f32; 3],
+    view_mode: u32,
+}
+
+/// Renderer of the shadow acne example
+pub struct ShadowScene {
+    shader: wgpu::ShaderModule,
+    shadow_pipeline_layout: wgpu::PipelineLayout,
+    shadow_pipeline: wgpu::RenderPipeline,
+    scene_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    uniform_buffer: wgpu::Buffer,
+    uniform_bind_group: wgpu::BindGroup,
+    shadow_map_layout: wgpu::BindGroupLayout,
+    /// Comparison samplers taking the nearest texel and filtering 2x2 texels
+    samplers: [wgpu::Sampler; 2],
+    shadow_map: wgpu::TextureView,
+    /// The shadow map with each sampler
+    shadow_map_bind_groups: [wgpu::BindGroup; 2],
+    depth_view: wgpu::TextureView,
+    size: (u32, u32),
+    /// Depth-stencil state and culling of the last shadow pipeline build,
+    /// which may have failed
+    attempted: (wgpu::DepthStencilState, CullMode),
+    /// Format the shadow pipeline in use renders to
+    pipeline_format: wgpu::TextureFormat,
+    /// Format and size of the shadow map
+    map_key: (wgpu::TextureFormat, u32),
+    errors: OperationErrors,
+    /// Depth-stencil state of the shadow pipeline: its format is the
+    /// shadow map's, and its depth bias is what this example is about
+    pub shadow_state: DepthStencilState,
+    /// Faces the shadow pass skips
+    pub cull_mode: CullMode,
+    pub shadow_map_size: u32,
+    /// Degrees around the vertical axis
+    pub light_azimuth: f32,
+    /// Degrees above the ground; the lower, the more the ground slopes
+    /// away from the light
+    pub light_elevation: f32,
+    /// Filter 2x2 shadow map texels when comparing
+    pub filtered: bool,
+    pub view: ShadowView,
+}
+
+impl ShadowScene {
+    /// Create the renderer drawing into `color_format` targets of `width`x`height`
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shadow Acne Shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADOW_SHADER.into()),
+        });
+
+        let (vertices, indices) = scene_mesh();
+        let buffer = |label, contents: &[u8], usage| {
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: contents.len() as u64,
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            queue.write_buffer(&buffer, 0, contents);
+            buffer
+        };
+        let vertex_buffer = buffer(
+            "Shadow Acne Vertex Buffer",
+            bytemuck::cast_slice(&vertices),
+            wgpu::BufferUsages::VERTEX,
+        );
+        let index_buffer = buffer(
+            "Shadow Acne Index Buffer",
+            bytemuck::cast_slice(&indices),
+            wgpu::BufferUsages::INDEX,
+        );
+        let uniform_buffer = buffer(
+            "Shadow Acne Uniform Buffer",
+            bytemuck::bytes_of(&Uniforms::zeroed()),
+            wgpu::BufferUsages::UNIFORM,
+        );
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let uniform_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Shadow Acne Uniform Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let shadow_map_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Shadow Map Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                    count: None,
+                },
+            ],
+        });
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Shadow Acne Uniform Bind Group"),
+            layout: &uniform_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let sampler = |label, filter| {
+            tracker.record(ApiCategory::Sampler, "create_sampler");
+            device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some(label),
+                mag_filter: filter,
+                min_filter: filter,
+                compare: Some(wgpu::CompareFunction::LessEqual),
+                ..Default::default()
+            })
+        };
+        let samplers = [
+            sampler("Shadow Map Nearest Sampler", wgpu::FilterMode::Nearest),
+            sampler("Shadow Map Linear Sampler", wgpu::FilterMode::Linear),
+        ];
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let shadow_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Shadow Pass Pipeline Layout"),
+                bind_group_layouts: &[Some(&uniform_layout)],
+                immediate_size: 0,
+            });
+        let scene_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Shadow Acne Scene Pipeline Layout"),
+                bind_group_layouts: &[Some(&uniform_layout), Some(&shadow_map_layout)],
+                immediate_size: 0,
+            });
+
+        let vertex_attributes =
+            wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x3];
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let scene_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Shadow Acne Scene Pipeline"),
+            layout: Some(&scene_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_scene"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<SceneVertex>() as u64,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &vertex_attributes,
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_scene"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        let shadow_state = DepthStencilState::new(SHADOW_FORMATS[0]);
+        let cull_mode = CullMode::None;
+        let shadow_pipeline = create_shadow_pipeline(
+            device,
+            &shader,
+            &shadow_pipeline_layout,
+            &shadow_state,
+            cull_mode,
+        );
+        let shadow_map_size = 1024;
+        let shadow_map = create_shadow_map(device, shadow_state.format, shadow_map_size);
+        let shadow_map_bind_groups =
+            create_shadow_map_bind_groups(device, &shadow_map_layout, &shadow_map, &samplers);
+
+        Self {
+            shader,
+            shadow_pipeline_layout,
+            shadow_pipeline,
+            scene_pipeline,
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+            uniform_buffer,
+            uniform_bind_group,
+            shadow_map_layout,
+            samplers,
+            shadow_map,
+            shadow_map_bind_groups,
+            depth_view: create_depth_texture(device, (width, height)),
+            size: (width, height),
+            attempted: (shadow_state.to_wgpu(), cull_mode),
+            pipeline_format: shadow_state.format,
+            map_key: (shadow_state.format, shadow_map_size),
+            errors: OperationErrors::new(),
+            shadow_state,
+            cull_mode,
+            shadow_map_size,
+            light_azimuth: 30.0,
+            light_elevation: 20.0,
+            filtered: false,
+            view: ShadowView::Lit,
+        }
+    }
+
+    /// Errors of building the shadow pipeline, under [`SHADOW_PIPELINE`]
+    ///
+    /// A non-zero depth bias clamp needs
+    /// [`wgpu::DownlevelFlags::DEPTH_BIAS_CLAMP`]; without it the build
+    /// fails and the previous pipeline stays in use.
+    pub fn errors(&self) -> &OperationErrors {
+        &self.errors
+    }
+
+    /// Set the depth bias from one of [`BIAS_PRESETS`]
+    pub fn apply_preset(&mut self, (_, distance, slope_scale, clamp): (&str, f32, f32, f32)) {
+        self.shadow_state.depth_bias = constant_for_distance(self.shadow_state.format, distance);
+        self.shadow_state.depth_bias_slope_scale = slope_scale;
+        self.shadow_state.depth_bias_clamp = clamp;
+    }
+
+    /// Size and format of the shadow map in use
+    pub fn shadow_map_info(&self) -> (u32, wgpu::TextureFormat) {
+        (self.map_key.1, self.map_key.0)
+    }
+
+    /// Update the camera and the light
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: Mat4) {
+        let light_dir = light_direction(self.light_azimuth, self.light_elevation);
+        let uniforms = Uniforms {
+            view_proj,
+            light_view_proj: light_view_proj(light_dir),
+            light_dir,
+            view_mode: self.view.index(),
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+    }
+
+    /// Render the shadow map, then the scene into `target`, a view of a
+    /// texture of the given size
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        self.rebuild(device, (width, height));
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Shadow Acne Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Shadow Map Pass"),
+                color_attachments: &[],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.shadow_map,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.shadow_pipeline);
+            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            tracker.record(ApiCategory::RenderPass, "draw_indexed");
+            render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+        }
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Shadow Acne Scene Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.scene_pipeline);
+            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            let sampler = usize::from(self.filtered);
+            render_pass.set_bind_group(1, &self.shadow_map_bind_groups[sampler], &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            tracker.record(ApiCategory::RenderPass, "draw_indexed");
+            render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+
+    /// Rebuild the shadow pipeline, the shadow map and the depth target
+    /// for settings and a frame size that changed since the last frame
+    fn rebuild(&mut self, device: &wgpu::Device, size: (u32, u32)) {
+        if self.size != size {
+            self.depth_view = create_depth_texture(device, size);
+            self.size = size;
+        }
+
+        let key = (self.shadow_state.to_wgpu(), self.cull_mode);
+        if key != self.attempted {
+            let pipeline = self.errors.scoped(device, SHADOW_PIPELINE, || {
+                create_shadow_pipeline(
+                    device,
+                    &self.shader,
+                    &self.shadow_pipeline_layout,
+                    &self.shadow_state,
+                    self.cull_mode,
+                )
+            });
+            if !self.errors.has_error(SHADOW_PIPELINE) {
+                self.shadow_pipeline = pipeline;
+                self.pipeline_format = self.shadow_state.format;
+            }
+            self.attempted = key;
+        }
+
+        // The map takes the format of the pipeline in use
+        let map_key = (self.pipeline_format, self.shadow_map_size);
+        if self.map_key != map_key {
+            self.shadow_map = create_shadow_map(device, map_key.0, map_key.1);
+            self.shadow_map_bind_groups = create_shadow_map_bind_groups(
+                device,
+                &self.shadow_map_layout,
+                &self.shadow_map,
+                &self.samplers,
+            );
+            self.map_key = map_key;
+        }
+    }
+}
+
+/// Depth-only pipeline rendering the scene from the light
+fn create_shadow_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    state: &DepthStencilState,
+    cull_mode: CullMode,
+) -> wgpu::RenderPipeline {
+    ApiCoverageTracker::global().record(ApiCategory::RenderPipeline, "create_render_pipeline");
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Shadow Map Pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_shadow"),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<SceneVertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float32x3],
+            }],
+            compilation_options: Default::default(),
+        },
+        fragment: None,
+        primitive: wgpu::PrimitiveState {
+            cull_mode: cull_mode.to_wgpu(),
+            ..Default::default()
+        },
+        depth_stencil: Some(state.to_wgpu()),
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+    })
+}
+
+fn create_shadow_map(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    size: u32,
+) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Shadow Map"),
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn create_shadow_map_bind_groups(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    shadow_map: &wgpu::TextureView,
+    samplers: &[wgpu::Sampler; 2],
+) -> [wgpu::BindGroup; 2] {
+    samplers.each_ref().map(|sampler| {
+        ApiCoverageTracker::global().record(ApiCategory::BindGroup, "create_bind_group");
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Shadow Map Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(shadow_map),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    })
+}
+
+fn create_depth_texture(device: &wgpu::Device, (width, height): (u32, u32)) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Shadow Acne Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_utils::dot;
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(SHADOW_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_uniform_layout() {
+        assert_eq!(std::mem::size_of::<Uniforms>(), 144);
+    }
+
+    #[test]
+    fn test_light_view_proj_covers_the_scene() {
+        let direction = light_direction(30.0, 20.0);
+        assert!((dot(direction, direction) - 1.0).abs() < 1e-5);
+        assert!((direction[1] - 20.0f32.to_radians().sin()).abs() < 1e-6);
+
+        let light = light_view_proj(direction);
+        let (vertices, _) = scene_mesh();
+        for vertex in vertices {
+            let [x, y, z] = transform_point(&light, vertex.position);
+            assert!(x.abs() <= 1.0 && y.abs() <= 1.0, "{:?}", vertex.position);
+            assert!((0.0..=1.0).contains(&z), "{:?}", vertex.position);
+        }
+        // Points nearer the light get smaller depths
+        let near = transform_point(&light, direction);
+        let far = transform_point(&light, direction.map(|c| -c));
+        assert!(near[2] < far[2]);
+    }
+
+    #[test]
+    fn test_constant_bias_units() {
+        let range = 2.0 * SCENE_RADIUS;
+        assert!(
+            (constant_bias_unit(wgpu::TextureFormat::Depth16Unorm) - range / 65535.0).abs() < 1e-9
+        );
+        assert!(
+            constant_bias_unit(wgpu::TextureFormat::Depth32Float)
+                < constant_bias_unit(wgpu::TextureFormat::Depth16Unorm) / 100.0
+        );
+        assert_eq!(
+            constant_for_distance(wgpu::TextureFormat::Depth16Unorm, range),
+            65535
+        );
+        assert_eq!(
+            constant_for_distance(wgpu::TextureFormat::Depth32Float, 0.0),
+            0
+        );
+    }
+
+    #[test]
+    fn test_scene_mesh() {
+        let (vertices, indices) = scene_mesh();
+        assert_eq!(indices.len() % 3, 0);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+        for vertex in &vertices {
+            assert!((dot(vertex.normal, vertex.normal) - 1.0).abs() < 1e-5);
+            assert!(vertex.position[1] >= -0.1 - 1e-6);
+        }
+    }
+}
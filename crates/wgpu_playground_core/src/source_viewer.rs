@@ -0,0 +1,610 @@
+//! Read-only viewer for the source of the built-in examples
+//!
+//! Every example lists its WGSL and the Rust files implementing it in the
+//! example registry, embedded with `include_str!`. The viewer shows one
+//! file at a time with line numbers and finds its sections, the top-level
+//! items and the methods of `impl` blocks for Rust, the functions, structs,
+//! bindings and constants for WGSL, to jump to. A section starts at the doc
+//! comments and attributes above its item.
+//!
+//! Sections are found line by line, without a parser: items are recognized
+//! at the start of a line, so WGSL embedded in a Rust raw string is skipped
+//! but items split across lines get the title of their first line.
+
+use std::ops::RangeInclusive;
+
+use crate::examples::{Example, SourceFile, SourceLanguage};
+
+/// Height of the code area in points
+const VIEWER_HEIGHT: f32 = 400.0;
+
+/// A place in a source file to jump to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// The item's kind and name, like `fn vs_main` or `impl Default for Camera`
+    pub title: String,
+    /// Line the section starts at, its first doc comment or attribute, from 0
+    pub line: usize,
+    /// Line of the item itself
+    pub item_line: usize,
+    /// 1 for methods inside an `impl` or `trait`, 0 otherwise
+    pub depth: usize,
+}
+
+impl Section {
+    /// Whether `name` is the section's title or the item name at its end
+    pub fn matches(&self, name: &str) -> bool {
+        self.title == name || self.title.rsplit(' ').next() == Some(name)
+    }
+}
+
+/// Find the sections of a file
+pub fn sections(file: &SourceFile) -> Vec<Section> {
+    match file.language {
+        SourceLanguage::Rust => rust_sections(file.contents),
+        SourceLanguage::Wgsl => wgsl_sections(file.contents),
+    }
+}
+
+fn rust_sections(source: &str) -> Vec<Section> {
+    let mut sections = Vec::new();
+    // Closing delimiter of the raw string the current line is in
+    let mut raw_string: Option<String> = None;
+    let mut docs_start = None;
+    let mut in_impl = false;
+
+    for (index, line) in source.lines().enumerate() {
+        if let Some(close) = &raw_string {
+            if line.contains(close.as_str()) {
+                raw_string = None;
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if trimmed.starts_with("///") || trimmed.starts_with("#[") {
+            docs_start.get_or_insert(index);
+            continue;
+        }
+
+        let title = match indent {
+            0 => rust_item(trimmed).inspect(|title| {
+                in_impl = title.starts_with("impl") || title.starts_with("trait ");
+            }),
+            4 if in_impl => rust_item(trimmed).filter(|title| title.starts_with("fn ")),
+            _ => None,
+        };
+        if let Some(title) = title {
+            sections.push(Section {
+                title,
+                line: docs_start.unwrap_or(index),
+                item_line: index,
+                depth: usize::from(indent > 0),
+            });
+        }
+        docs_start = None;
+        raw_string = opened_raw_string(line);
+    }
+    sections
+}
+
+/// Title of the Rust item declared at the start of a line
+fn rust_item(line: &str) -> Option<String> {
+    let mut rest = line;
+    for visibility in ["pub(crate) ", "pub(super) ", "pub "] {
+        if let Some(stripped) = rest.strip_prefix(visibility) {
+            rest = stripped;
+            break;
+        }
+    }
+    for qualifier in ["const ", "async ", "unsafe "] {
+        if rest
+            .strip_prefix(qualifier)
+            .is_some_and(|r| r.starts_with("fn "))
+        {
+            rest = &rest[qualifier.len()..];
+        }
+    }
+
+    if rest == "impl" || rest.starts_with("impl ") || rest.starts_with("impl<") {
+        let header = rest.split('{').next().unwrap_or(rest);
+        return Some(header.trim_end().to_string());
+    }
+    let (keyword, tail) = rest.split_once(' ')?;
+    if !matches!(
+        keyword,
+        "fn" | "struct" | "enum" | "trait" | "mod" | "const" | "static" | "type"
+    ) {
+        return None;
+    }
+    let name = identifier(tail)?;
+    Some(format!("{} {}", keyword, name))
+}
+
+/// Closing delimiter of a raw string opened and not closed on `line`
+fn opened_raw_string(line: &str) -> Option<String> {
+    let start = line.find("r#")?;
+    let hashes = line[start + 1..].chars().take_while(|&c| c == '#').count();
+    let body = start + 1 + hashes;
+    if !line[body..].starts_with('"') {
+        return None;
+    }
+    let close = format!("\"{}", "#".repeat(hashes));
+    (!line[body + 1..].contains(close.as_str())).then_some(close)
+}
+
+fn wgsl_sections(source: &str) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut docs_start = None;
+    let mut stage = None;
+
+    for (index, line) in source.lines().enumerate() {
+        if line.starts_with("//") {
+            docs_start.get_or_insert(index);
+            continue;
+        }
+        let (attribute_stage, item) = strip_attributes(line);
+        stage = attribute_stage.or(stage);
+        if item.is_empty() && line.starts_with('@') {
+            // Attributes on a line of their own, like `@compute @workgroup_size(64)`
+            docs_start.get_or_insert(index);
+            continue;
+        }
+
+        if let Some(title) = wgsl_item(item) {
+            let title = match stage {
+                Some(stage) if title.starts_with("fn ") => format!("@{} {}", stage, title),
+                _ => title,
+            };
+            sections.push(Section {
+                title,
+                line: docs_start.unwrap_or(index),
+                item_line: index,
+                depth: 0,
+            });
+        }
+        docs_start = None;
+        stage = None;
+    }
+    sections
+}
+
+/// Split the attributes off the start of a line, returning the shader stage
+/// among them and the rest of the line
+fn strip_attributes(line: &str) -> (Option<&'static str>, &str) {
+    let mut stage = None;
+    let mut rest = line;
+    while let Some(attribute) = rest.strip_prefix('@') {
+        let name = identifier(attribute).unwrap_or("");
+        stage = match name {
+            "vertex" => Some("vertex"),
+            "fragment" => Some("fragment"),
+            "compute" => Some("compute"),
+            _ => stage,
+        };
+        rest = &attribute[name.len()..];
+        if rest.starts_with('(') {
+            rest = rest.find(')').map_or("", |end| &rest[end + 1..]);
+        }
+        rest = rest.trim_start();
+    }
+    (stage, rest)
+}
+
+/// Title of the WGSL declaration at the start of a line
+fn wgsl_item(line: &str) -> Option<String> {
+    // `var<storage, read_write> name` has a space inside the address space
+    if let Some(rest) = line
+        .strip_prefix("var")
+        .filter(|rest| rest.starts_with(['<', ' ']))
+    {
+        let rest = match rest.strip_prefix('<') {
+            Some(inner) => &inner[inner.find('>')? + 1..],
+            None => rest,
+        };
+        return Some(format!("var {}", identifier(rest.trim_start())?));
+    }
+    let (keyword, tail) = line.split_once(' ')?;
+    if !matches!(keyword, "fn" | "struct" | "const" | "override" | "alias") {
+        return None;
+    }
+    Some(format!("{} {}", keyword, identifier(tail)?))
+}
+
+/// The identifier at the start of `text`
+fn identifier(text: &str) -> Option<&str> {
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    (end > 0).then(|| &text[..end])
+}
+
+/// Source files of the selected example with the open file's sections
+pub struct SourceViewer {
+    example_id: Option<&'static str>,
+    files: Vec<SourceFile>,
+    file: usize,
+    lines: Vec<&'static str>,
+    sections: Vec<Section>,
+    /// Lines of the section jumped to last, highlighted
+    current: Option<RangeInclusive<usize>>,
+    /// Line to scroll to on the next frame
+    scroll_to: Option<usize>,
+}
+
+impl Default for SourceViewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourceViewer {
+    pub fn new() -> Self {
+        Self {
+            example_id: None,
+            files: Vec::new(),
+            file: 0,
+            lines: Vec::new(),
+            sections: Vec::new(),
+            current: None,
+            scroll_to: None,
+        }
+    }
+
+    /// Show the sources of `example`, unless they are shown already
+    pub fn open(&mut self, example: &Example) {
+        if self.example_id == Some(example.id) {
+            return;
+        }
+        self.example_id = Some(example.id);
+        self.files = example.source_files();
+        self.select_file(0);
+    }
+
+    /// Files of the open example, the WGSL first
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// The file shown, if an example is open
+    pub fn file(&self) -> Option<&SourceFile> {
+        self.files.get(self.file)
+    }
+
+    /// Show another file of the example at the item it starts at
+    pub fn select_file(&mut self, index: usize) {
+        let Some(file) = self.files.get(index).copied() else {
+            return;
+        };
+        self.file = index;
+        self.lines = file.contents.lines().collect();
+        self.sections = sections(&file);
+        self.current = None;
+        self.scroll_to = Some(0);
+        if let Some(item) = file.start_at {
+            self.jump_to(item);
+        }
+    }
+
+    /// Sections of the file shown
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Scroll to the first section whose title or name is `name`
+    pub fn jump_to(&mut self, name: &str) -> bool {
+        match self
+            .sections
+            .iter()
+            .position(|section| section.matches(name))
+        {
+            Some(index) => {
+                self.jump_to_section(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn jump_to_section(&mut self, index: usize) {
+        let section = &self.sections[index];
+        self.current = Some(section.line..=section.item_line);
+        self.scroll_to = Some(section.line);
+    }
+
+    /// Line the viewer scrolls to on its next frame
+    pub fn pending_scroll(&self) -> Option<usize> {
+        self.scroll_to
+    }
+
+    /// File tabs, section picker and the code of `example`
+    pub fn ui(&mut self, ui: &mut egui::Ui, example: &Example) {
+        self.open(example);
+
+        let mut selected_file = None;
+        ui.horizontal_wrapped(|ui| {
+            for (index, file) in self.files.iter().enumerate() {
+                let icon = match file.language {
+                    SourceLanguage::Rust => "🦀",
+                    SourceLanguage::Wgsl => "🎨",
+                };
+                if ui
+                    .selectable_label(index == self.file, format!("{} {}", icon, file.name))
+                    .on_hover_text(format!("{} source", file.language))
+                    .clicked()
+                    && index != self.file
+                {
+                    selected_file = Some(index);
+                }
+            }
+        });
+        if let Some(index) = selected_file {
+            self.select_file(index);
+        }
+
+        let mut selected_section = None;
+        ui.horizontal(|ui| {
+            ui.label("Jump to:");
+            let current = self.current.as_ref().and_then(|lines| {
+                self.sections
+                    .iter()
+                    .find(|section| section.line == *lines.start())
+            });
+            egui::ComboBox::from_id_salt("example_source_sections")
+                .selected_text(current.map_or("Top of file", |section| section.title.as_str()))
+                .width(280.0)
+                .show_ui(ui, |ui| {
+                    for (index, section) in self.sections.iter().enumerate() {
+                        let label = format!("{}{}", "    ".repeat(section.depth), section.title);
+                        if ui
+                            .selectable_label(current == Some(section), label)
+                            .clicked()
+                        {
+                            selected_section = Some(index);
+                        }
+                    }
+                });
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} lines, {} sections",
+                    self.lines.len(),
+                    self.sections.len()
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+        });
+        if let Some(index) = selected_section {
+            self.jump_to_section(index);
+        }
+
+        self.code_ui(ui);
+    }
+
+    fn code_ui(&mut self, ui: &mut egui::Ui) {
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        let mut area = egui::ScrollArea::both()
+            .id_salt(("example_source", self.example_id, self.file))
+            .max_height(VIEWER_HEIGHT)
+            .auto_shrink([false, true]);
+        if let Some(line) = self.scroll_to.take() {
+            let row_spacing = row_height + ui.spacing().item_spacing.y;
+            area = area.vertical_scroll_offset(line as f32 * row_spacing);
+        }
+
+        let digits = self.lines.len().to_string().len();
+        let highlight = ui.visuals().selection.bg_fill.gamma_multiply(0.4);
+        area.show_rows(ui, row_height, self.lines.len(), |ui, rows| {
+            for row in rows {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(format!("{:>width$}", row + 1, width = digits))
+                            .monospace()
+                            .color(egui::Color32::GRAY),
+                    );
+                    let mut code = egui::RichText::new(self.lines[row]).monospace();
+                    if self
+                        .current
+                        .as_ref()
+                        .is_some_and(|lines| lines.contains(&row))
+                    {
+                        code = code.background_color(highlight);
+                    }
+                    ui.add(egui::Label::new(code).extend());
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::get_all_examples;
+
+    const RUST_SOURCE: &str = r##"//! Module docs
+use std::fmt;
+
+/// A shader
+pub const SHADER: &str = r#"
+struct NotRust {
+    x: f32,
+}
+"#;
+
+/// Docs
+#[derive(Debug)]
+pub struct Scene {
+    size: f32,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        fn helper() {}
+        Self { size: 1.0 }
+    }
+
+    const fn size(&self) -> f32 {
+        self.size
+    }
+}
+
+impl<T: Copy> From<T> for Scene {
+    fn from(_: T) -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn render(scene: &Scene) {}
+
+#[cfg(test)]
+mod tests {
+    fn test_scene() {}
+}
+"##;
+
+    const WGSL_SOURCE: &str = "// Inputs
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+}
+
+@group(0) @binding(0) var<storage, read_write> counts: array<u32>;
+@group(0) @binding(1)
+var<uniform> params: Params;
+const SCALE: f32 = 2.0;
+
+// The vertex stage
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    return out;
+}
+
+@compute @workgroup_size(64)
+fn main() {}
+
+fn helper() -> f32 {
+    return 1.0;
+}
+";
+
+    fn titles(sections: &[Section]) -> Vec<&str> {
+        sections
+            .iter()
+            .map(|section| section.title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_sections() {
+        let sections = sections(&SourceFile::rust("scene.rs", RUST_SOURCE));
+        assert_eq!(
+            titles(&sections),
+            vec![
+                "const SHADER",
+                "struct Scene",
+                "impl Scene",
+                "fn new",
+                "fn size",
+                "impl<T: Copy> From<T> for Scene",
+                "fn from",
+                "fn render",
+                "mod tests",
+            ]
+        );
+        // Sections start at their docs and attributes
+        assert_eq!((sections[0].line, sections[0].item_line), (3, 4));
+        assert_eq!((sections[1].line, sections[1].item_line), (10, 12));
+        assert_eq!(sections[8].line, sections[8].item_line - 1);
+        assert_eq!(sections[3].depth, 1);
+        assert_eq!(sections[5].depth, 0);
+    }
+
+    #[test]
+    fn test_wgsl_sections() {
+        let sections = sections(&SourceFile::wgsl("shader.wgsl", WGSL_SOURCE));
+        assert_eq!(
+            titles(&sections),
+            vec![
+                "struct VertexOutput",
+                "var counts",
+                "var params",
+                "const SCALE",
+                "@vertex fn vs_main",
+                "@compute fn main",
+                "fn helper",
+            ]
+        );
+        assert_eq!((sections[0].line, sections[0].item_line), (0, 1));
+        assert_eq!((sections[2].line, sections[2].item_line), (6, 7));
+        assert_eq!((sections[4].line, sections[4].item_line), (10, 12));
+        assert!(sections[4].matches("vs_main"));
+        assert!(!sections[4].matches("main"));
+    }
+
+    #[test]
+    fn test_every_example_opens_at_its_start() {
+        let mut viewer = SourceViewer::new();
+        for example in get_all_examples() {
+            viewer.open(&example);
+            assert!(
+                viewer
+                    .files()
+                    .iter()
+                    .any(|file| file.language == SourceLanguage::Rust),
+                "{} has no Rust source",
+                example.id
+            );
+            for index in 0..viewer.files().len() {
+                viewer.select_file(index);
+                let file = *viewer.file().unwrap();
+                assert!(
+                    !viewer.sections().is_empty(),
+                    "{} has no sections",
+                    file.name
+                );
+                let line = viewer.pending_scroll().unwrap();
+                if let Some(item) = file.start_at {
+                    assert!(
+                        viewer
+                            .sections()
+                            .iter()
+                            .any(|section| section.line == line && section.matches(item)),
+                        "{} does not start at {}",
+                        example.id,
+                        item
+                    );
+                } else {
+                    assert_eq!(line, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_jump_to() {
+        let example = get_all_examples()
+            .into_iter()
+            .find(|example| example.id == "triangle")
+            .unwrap();
+        let mut viewer = SourceViewer::new();
+        viewer.open(&example);
+        assert_eq!(viewer.file().unwrap().language, SourceLanguage::Wgsl);
+        assert!(viewer.jump_to("vs_main"));
+        let section = viewer
+            .sections()
+            .iter()
+            .find(|section| section.title == "@vertex fn vs_main")
+            .unwrap();
+        assert_eq!(viewer.pending_scroll(), Some(section.line));
+        let item = example.source_code.lines().nth(section.item_line).unwrap();
+        assert!(item.starts_with("fn vs_main"));
+        assert!(!viewer.jump_to("no_such_function"));
+
+        // Reopening the example keeps the file shown
+        viewer.select_file(1);
+        viewer.open(&example);
+        assert_eq!(viewer.file().unwrap().name, "rendering.rs");
+    }
+}
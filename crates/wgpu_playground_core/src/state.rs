@@ -4,18 +4,18 @@
 /// allowing users to save and load their work. It includes serializable
 /// versions of panel configurations and conversion methods.
 ///
-/// # Limitations
-///
-/// Some enum values (TextureFormat, TextureDimension, AddressMode, FilterMode, etc.)
-/// are serialized as strings but not parsed back during import to avoid complexity.
-/// These fields will retain their default values when loading state.
-/// The string values are preserved in JSON for reference and future enhancement.
+/// Enum values (TextureFormat, AddressMode, BlendFactor, etc.) are stored as
+/// their `Debug` names and parsed back with [`parse_variant`]. Unknown names
+/// leave the panel's current value unchanged.
 use base64::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use crate::api_coverage::CoverageData;
 use crate::learning_path::LearningProgress;
+use crate::shader_history::ShaderHistory;
+use crate::theme::ThemePalette;
 use crate::tutorial::TutorialState;
 
 /// Serializable version of BufferPanel state
@@ -52,6 +52,9 @@ pub struct TexturePanelState {
     pub usage_texture_binding: bool,
     pub usage_storage_binding: bool,
     pub usage_render_attachment: bool,
+    /// Loaded image file (PNG or JPEG) encoded as base64, if any
+    #[serde(default)]
+    pub image_data: Option<String>,
 }
 
 /// Serializable version of SamplerPanel state
@@ -76,6 +79,9 @@ pub struct ShaderEditorState {
     pub source_code: String,
     pub label: String,
     pub file_path: String,
+    /// Snapshots of every shader edited in the project
+    #[serde(default)]
+    pub history: ShaderHistory,
 }
 
 /// Serializable version of RenderPipelinePanel state
@@ -87,6 +93,9 @@ pub struct RenderPipelinePanelState {
     pub topology: String,
     pub cull_mode: String,
     pub front_face: String,
+    /// Missing in states saved before polygon modes were configurable
+    #[serde(default)]
+    pub polygon_mode: String,
     pub enable_depth_stencil: bool,
     pub depth_format: String,
     pub depth_write_enabled: bool,
@@ -124,18 +133,53 @@ pub struct ComputePipelinePanelState {
     pub entry_point: String,
 }
 
+/// Serializable bind group layout entry
+///
+/// `binding_type` is one of `UniformBuffer`, `StorageBuffer`, `Texture`,
+/// `Sampler` or `StorageTexture`. Textures are filterable 2D float textures
+/// and samplers are filtering samplers, as in the bind group panel.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BindGroupEntryState {
+    pub binding: u32,
+    pub visibility_vertex: bool,
+    pub visibility_fragment: bool,
+    pub visibility_compute: bool,
+    pub binding_type: String,
+    /// Whether a storage buffer is read-only
+    #[serde(default)]
+    pub read_only: bool,
+    /// Access of a storage texture, as its `StorageTextureAccess` name
+    #[serde(default)]
+    pub access: Option<String>,
+}
+
 /// Serializable version of BindGroupPanel state
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BindGroupPanelState {
     pub label: String,
-    // Additional bind group configuration fields can be added here
+    /// Missing in states saved before layouts were exported
+    #[serde(default)]
+    pub entries: Vec<BindGroupEntryState>,
 }
 
 /// Serializable version of BindGroupLayoutPanel state
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BindGroupLayoutPanelState {
     pub label: String,
-    // Additional bind group layout configuration fields can be added here
+    /// Missing in states saved before layouts were exported
+    #[serde(default)]
+    pub entries: Vec<BindGroupEntryState>,
+}
+
+/// Serializable version of the device features and limits selection
+///
+/// Features are stored by their `wgpu::Features` flag names and limits by
+/// their `wgpu::Limits` field names. Only limits that differ from the
+/// defaults are stored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceConfigState {
+    pub features: Vec<String>,
+    pub limits: BTreeMap<String, u64>,
 }
 
 /// UI theme preference
@@ -144,6 +188,8 @@ pub enum Theme {
     Light,
     #[default]
     Dark,
+    /// The user's palette, stored in [`PlaygroundState::custom_theme`]
+    Custom,
 }
 
 /// Complete serializable playground state
@@ -154,6 +200,12 @@ pub struct PlaygroundState {
     /// UI theme preference
     #[serde(default)]
     pub theme: Theme,
+    /// UI language code, e.g. `de`; English when not set
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Palette of the custom theme, kept while another theme is selected
+    #[serde(default)]
+    pub custom_theme: Option<ThemePalette>,
     /// Buffer panel state
     pub buffer_panel: Option<BufferPanelState>,
     /// Texture panel state
@@ -179,6 +231,9 @@ pub struct PlaygroundState {
     /// Learning progress
     #[serde(default)]
     pub learning_progress: Option<LearningProgress>,
+    /// Device features and limits requested at startup
+    #[serde(default)]
+    pub device_config: Option<DeviceConfigState>,
 }
 
 impl Default for PlaygroundState {
@@ -186,6 +241,8 @@ impl Default for PlaygroundState {
         Self {
             version: "1.0".to_string(),
             theme: Theme::default(),
+            language: None,
+            custom_theme: None,
             buffer_panel: None,
             texture_panel: None,
             sampler_panel: None,
@@ -197,6 +254,7 @@ impl Default for PlaygroundState {
             api_coverage: None,
             tutorial_state: None,
             learning_progress: None,
+            device_config: None,
         }
     }
 }
@@ -345,10 +403,32 @@ impl PlaygroundState {
     }
 }
 
+/// Find the option whose `Debug` name matches a saved enum string
+pub fn parse_variant<T: std::fmt::Debug + Copy>(name: &str, options: &[T]) -> Option<T> {
+    options
+        .iter()
+        .copied()
+        .find(|option| format!("{:?}", option) == name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_variant() {
+        let options = [
+            wgpu::TextureDimension::D1,
+            wgpu::TextureDimension::D2,
+            wgpu::TextureDimension::D3,
+        ];
+        assert_eq!(
+            parse_variant("D3", &options),
+            Some(wgpu::TextureDimension::D3)
+        );
+        assert_eq!(parse_variant("D4", &options), None);
+    }
+
     #[test]
     fn test_state_serialization() {
         let state = PlaygroundState {
@@ -385,6 +465,7 @@ mod tests {
                 source_code: "@vertex fn main() {}".to_string(),
                 label: "my_shader".to_string(),
                 file_path: "shader.wgsl".to_string(),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -473,6 +554,7 @@ fn fs_main() -> @location(0) vec4<f32> {
                 source_code: shader_code.to_string(),
                 label: "red_shader".to_string(),
                 file_path: "shader.wgsl".to_string(),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -591,6 +673,7 @@ fn fs_main() -> @location(0) vec4<f32> {
                 source_code: "@vertex fn main() {}".to_string(),
                 label: "test_shader".to_string(),
                 file_path: "test.wgsl".to_string(),
+                ..Default::default()
             }),
             ..Default::default()
         };
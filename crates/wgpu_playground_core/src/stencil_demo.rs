@@ -0,0 +1,729 @@
+//! Stencil buffer step-through: a few draws and the stencil buffer after each
+//!
+//! A [`StencilStep`] draws one shape with its own stencil face state,
+//! reference and masks, optionally without touching the color target, the
+//! usual way a mask is written before the content that is clipped by it.
+//! [`execute`] runs the steps in order and captures a [`StencilSnapshot`]
+//! after each one.
+//!
+//! Stencil aspects can't be copied to buffers everywhere (GL lacks it), so
+//! the snapshot is decoded on the GPU instead: one fullscreen draw per
+//! possible value with `compare: Equal` writes that value into an `R8Unorm`
+//! texture wherever the stencil holds it, and that texture is read back.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::render_pipeline::{CompareFunction, StencilFaceState, StencilOperation};
+
+/// Width and height of the demo targets
+pub const DEMO_SIZE: u32 = 128;
+
+/// Depth-stencil format of the demo; the depth test always passes
+pub const STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
+
+const COLOR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const VALUE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+const CLEAR_COLOR: [f32; 4] = [0.1, 0.1, 0.12, 1.0];
+
+const STENCIL_DEMO_SHADER: &str = r#"
+struct ShapeOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+@vertex
+fn vs_shape(@location(0) position: vec2<f32>, @location(1) color: vec4<f32>) -> ShapeOutput {
+    var out: ShapeOutput;
+    out.position = vec4<f32>(position, 0.0, 1.0);
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_shape(in: ShapeOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
+
+struct ValueOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) value: u32,
+}
+
+// The instance index is the stencil value the draw is testing for
+@vertex
+fn vs_value(@builtin(vertex_index) vertex: u32, @builtin(instance_index) value: u32) -> ValueOutput {
+    let uv = vec2<f32>(f32((vertex << 1u) & 2u), f32(vertex & 2u));
+    var out: ValueOutput;
+    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.value = value;
+    return out;
+}
+
+@fragment
+fn fs_value(in: ValueOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(f32(in.value) / 255.0, 0.0, 0.0, 1.0);
+}
+"#;
+
+/// Shapes a step can draw, all counter-clockwise so they face the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StencilShape {
+    Circle,
+    Bar,
+    Triangle,
+    FullScreen,
+}
+
+impl StencilShape {
+    pub fn all() -> [StencilShape; 4] {
+        [
+            StencilShape::Circle,
+            StencilShape::Bar,
+            StencilShape::Triangle,
+            StencilShape::FullScreen,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StencilShape::Circle => "Circle",
+            StencilShape::Bar => "Bar",
+            StencilShape::Triangle => "Triangle",
+            StencilShape::FullScreen => "Full screen",
+        }
+    }
+
+    /// Triangle list in normalized device coordinates
+    pub fn vertices(&self) -> Vec<[f32; 2]> {
+        let rect = |x0: f32, y0: f32, x1: f32, y1: f32| {
+            vec![[x0, y0], [x1, y0], [x1, y1], [x0, y0], [x1, y1], [x0, y1]]
+        };
+        match self {
+            StencilShape::Circle => {
+                let (center, radius, segments) = ([-0.25, 0.15], 0.6, 48);
+                let point = |i: u32| {
+                    let angle = i as f32 / segments as f32 * std::f32::consts::TAU;
+                    [
+                        center[0] + radius * angle.cos(),
+                        center[1] + radius * angle.sin(),
+                    ]
+                };
+                (0..segments)
+                    .flat_map(|i| [center, point(i), point(i + 1)])
+                    .collect()
+            }
+            StencilShape::Bar => rect(-0.95, -0.3, 0.95, 0.1),
+            StencilShape::Triangle => vec![[0.1, 0.95], [-0.75, -0.9], [0.95, -0.9]],
+            StencilShape::FullScreen => rect(-1.0, -1.0, 1.0, 1.0),
+        }
+    }
+}
+
+/// One draw of the demo
+#[derive(Debug, Clone, PartialEq)]
+pub struct StencilStep {
+    pub name: String,
+    pub shape: StencilShape,
+    pub face: StencilFaceState,
+    /// Reference value; only the low 8 bits reach the stencil buffer
+    pub reference: u32,
+    pub read_mask: u32,
+    pub write_mask: u32,
+    /// Whether the draw writes color or only the stencil
+    pub writes_color: bool,
+    pub color: [f32; 4],
+}
+
+impl StencilStep {
+    pub fn new(name: &str, shape: StencilShape, face: StencilFaceState, reference: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            shape,
+            face,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0xFF,
+            writes_color: true,
+            color: [0.95, 0.55, 0.15, 1.0],
+        }
+    }
+
+    /// The same draw without color writes
+    pub fn stencil_only(mut self) -> Self {
+        self.writes_color = false;
+        self
+    }
+
+    /// Stencil value after this draw covers a texel holding `value`
+    pub fn apply(&self, value: u8) -> u8 {
+        let passed = stencil_test(self.face.compare, self.reference, value, self.read_mask);
+        // The depth test always passes, so the depth fail op never runs
+        let op = if passed {
+            self.face.pass_op
+        } else {
+            self.face.fail_op
+        };
+        apply_op(op, value, self.reference, self.write_mask)
+    }
+}
+
+/// Face state that always passes and applies `pass_op`
+pub fn always(pass_op: StencilOperation) -> StencilFaceState {
+    StencilFaceState {
+        compare: CompareFunction::Always,
+        fail_op: StencilOperation::Keep,
+        depth_fail_op: StencilOperation::Keep,
+        pass_op,
+    }
+}
+
+/// A circle and a bar written only into the stencil, then a triangle
+/// clipped to where exactly one of them was drawn
+pub fn example_steps() -> Vec<StencilStep> {
+    vec![
+        StencilStep::new(
+            "Mask: circle",
+            StencilShape::Circle,
+            always(StencilOperation::Replace),
+            1,
+        )
+        .stencil_only(),
+        StencilStep::new(
+            "Mask: bar",
+            StencilShape::Bar,
+            always(StencilOperation::IncrementClamp),
+            0,
+        )
+        .stencil_only(),
+        StencilStep::new(
+            "Content: triangle",
+            StencilShape::Triangle,
+            StencilFaceState {
+                compare: CompareFunction::Equal,
+                ..always(StencilOperation::Keep)
+            },
+            1,
+        ),
+    ]
+}
+
+/// The stencil test: `reference & read_mask` compared against `value & read_mask`
+pub fn stencil_test(compare: CompareFunction, reference: u32, value: u8, read_mask: u32) -> bool {
+    let reference = reference & read_mask & 0xFF;
+    let value = value as u32 & read_mask;
+    match compare {
+        CompareFunction::Never => false,
+        CompareFunction::Less => reference < value,
+        CompareFunction::Equal => reference == value,
+        CompareFunction::LessEqual => reference <= value,
+        CompareFunction::Greater => reference > value,
+        CompareFunction::NotEqual => reference != value,
+        CompareFunction::GreaterEqual => reference >= value,
+        CompareFunction::Always => true,
+    }
+}
+
+/// New stencil value after `op`, keeping the bits outside `write_mask`
+pub fn apply_op(op: StencilOperation, value: u8, reference: u32, write_mask: u32) -> u8 {
+    let result = match op {
+        StencilOperation::Keep => value,
+        StencilOperation::Zero => 0,
+        StencilOperation::Replace => reference as u8,
+        StencilOperation::Invert => !value,
+        StencilOperation::IncrementClamp => value.saturating_add(1),
+        StencilOperation::DecrementClamp => value.saturating_sub(1),
+        StencilOperation::IncrementWrap => value.wrapping_add(1),
+        StencilOperation::DecrementWrap => value.wrapping_sub(1),
+    };
+    let mask = write_mask as u8;
+    (value & !mask) | (result & mask)
+}
+
+/// Color of a stencil value in the overlay; 0 is left transparent
+pub fn stencil_color(value: u8) -> [u8; 4] {
+    const PALETTE: [[u8; 3]; 7] = [
+        [230, 60, 60],
+        [60, 200, 90],
+        [70, 120, 240],
+        [240, 210, 60],
+        [200, 80, 220],
+        [60, 210, 220],
+        [250, 140, 50],
+    ];
+    match value {
+        0 => [0, 0, 0, 0],
+        255 => [255, 255, 255, 255],
+        v => {
+            let [r, g, b] = PALETTE[(v as usize - 1) % PALETTE.len()];
+            [r, g, b, 255]
+        }
+    }
+}
+
+/// Color and stencil contents after a step
+#[derive(Debug, Clone, PartialEq)]
+pub struct StencilSnapshot {
+    pub width: u32,
+    pub height: u32,
+    /// One stencil value per texel, row by row
+    pub stencil: Vec<u8>,
+    /// RGBA8 color per texel, row by row
+    pub color: Vec<u8>,
+}
+
+impl StencilSnapshot {
+    pub fn stencil_at(&self, x: u32, y: u32) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.stencil[(y * self.width + x) as usize])
+    }
+
+    pub fn color_at(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        (x < self.width && y < self.height).then(|| {
+            let i = (y * self.width + x) as usize * 4;
+            [
+                self.color[i],
+                self.color[i + 1],
+                self.color[i + 2],
+                self.color[i + 3],
+            ]
+        })
+    }
+
+    /// Texel count of every stencil value present, lowest value first
+    pub fn histogram(&self) -> Vec<(u8, usize)> {
+        let mut counts = [0usize; 256];
+        for &value in &self.stencil {
+            counts[value as usize] += 1;
+        }
+        (0..=255u8)
+            .filter(|&v| counts[v as usize] > 0)
+            .map(|v| (v, counts[v as usize]))
+            .collect()
+    }
+
+    /// Color with the stencil values laid over it at `opacity`
+    pub fn overlay_rgba(&self, opacity: f32) -> Vec<u8> {
+        self.color
+            .chunks_exact(4)
+            .zip(&self.stencil)
+            .flat_map(|(color, &value)| {
+                let tint = stencil_color(value);
+                let t = opacity.clamp(0.0, 1.0) * tint[3] as f32 / 255.0;
+                let mix = |c: u8, s: u8| (c as f32 * (1.0 - t) + s as f32 * t).round() as u8;
+                [
+                    mix(color[0], tint[0]),
+                    mix(color[1], tint[1]),
+                    mix(color[2], tint[2]),
+                    255,
+                ]
+            })
+            .collect()
+    }
+}
+
+fn create_target(device: &wgpu::Device, label: &str, format: wgpu::TextureFormat) -> wgpu::Texture {
+    let usage = if format == STENCIL_FORMAT {
+        wgpu::TextureUsages::RENDER_ATTACHMENT
+    } else {
+        wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC
+    };
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: DEMO_SIZE,
+            height: DEMO_SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    })
+}
+
+fn depth_stencil_state(
+    face: wgpu::StencilFaceState,
+    read_mask: u32,
+    write_mask: u32,
+) -> wgpu::DepthStencilState {
+    wgpu::DepthStencilState {
+        format: STENCIL_FORMAT,
+        depth_write_enabled: Some(false),
+        depth_compare: Some(wgpu::CompareFunction::Always),
+        stencil: wgpu::StencilState {
+            front: face,
+            back: face,
+            read_mask,
+            write_mask,
+        },
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    module: &wgpu::ShaderModule,
+    step: Option<&StencilStep>,
+) -> wgpu::RenderPipeline {
+    let (vertex, fragment, format, writes, depth_stencil) = match step {
+        Some(step) => (
+            "vs_shape",
+            "fs_shape",
+            COLOR_FORMAT,
+            if step.writes_color {
+                wgpu::ColorWrites::ALL
+            } else {
+                wgpu::ColorWrites::empty()
+            },
+            depth_stencil_state(step.face.to_wgpu(), step.read_mask, step.write_mask),
+        ),
+        None => {
+            let equal = wgpu::StencilFaceState {
+                compare: wgpu::CompareFunction::Equal,
+                fail_op: wgpu::StencilOperation::Keep,
+                depth_fail_op: wgpu::StencilOperation::Keep,
+                pass_op: wgpu::StencilOperation::Keep,
+            };
+            (
+                "vs_value",
+                "fs_value",
+                VALUE_FORMAT,
+                wgpu::ColorWrites::ALL,
+                depth_stencil_state(equal, 0xFF, 0),
+            )
+        }
+    };
+    let buffers: &[wgpu::VertexBufferLayout] = if step.is_some() {
+        &[wgpu::VertexBufferLayout {
+            array_stride: 6 * 4,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4],
+        }]
+    } else {
+        &[]
+    };
+    ApiCoverageTracker::global().record(ApiCategory::RenderPipeline, "create_render_pipeline");
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Stencil Demo Pipeline"),
+        layout: None,
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex),
+            buffers,
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: writes,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: Some(depth_stencil),
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+    })
+}
+
+fn color_attachment(
+    view: &wgpu::TextureView,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> Option<wgpu::RenderPassColorAttachment<'_>> {
+    Some(wgpu::RenderPassColorAttachment {
+        view,
+        depth_slice: None,
+        resolve_target: None,
+        ops: wgpu::Operations {
+            load,
+            store: wgpu::StoreOp::Store,
+        },
+    })
+}
+
+fn stencil_attachment(
+    view: &wgpu::TextureView,
+    load: wgpu::LoadOp<u32>,
+) -> Option<wgpu::RenderPassDepthStencilAttachment<'_>> {
+    Some(wgpu::RenderPassDepthStencilAttachment {
+        view,
+        depth_ops: Some(wgpu::Operations {
+            load: wgpu::LoadOp::Clear(1.0),
+            store: wgpu::StoreOp::Discard,
+        }),
+        stencil_ops: Some(wgpu::Operations {
+            load,
+            store: wgpu::StoreOp::Store,
+        }),
+    })
+}
+
+fn copy_to_buffer(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+    texel_size: u32,
+) -> (wgpu::Buffer, u32) {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_bytes_per_row = (DEMO_SIZE * texel_size).div_ceil(align) * align;
+    let tracker = ApiCoverageTracker::global();
+    tracker.record(ApiCategory::Buffer, "create_buffer");
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Stencil Demo Readback Buffer"),
+        size: (padded_bytes_per_row * DEMO_SIZE) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    tracker.record(ApiCategory::CommandEncoder, "copy_texture_to_buffer");
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(DEMO_SIZE),
+            },
+        },
+        texture.size(),
+    );
+    (buffer, padded_bytes_per_row)
+}
+
+fn read_buffer(buffer: &wgpu::Buffer, padded_bytes_per_row: u32, row_bytes: u32) -> Vec<u8> {
+    let data = buffer.slice(..).get_mapped_range();
+    let mut texels = Vec::with_capacity((row_bytes * DEMO_SIZE) as usize);
+    for row in data.chunks(padded_bytes_per_row as usize) {
+        texels.extend_from_slice(&row[..row_bytes as usize]);
+    }
+    drop(data);
+    buffer.unmap();
+    texels
+}
+
+/// Run the steps in order and capture the color and stencil after each
+///
+/// Blocks until the snapshots have been read back.
+pub fn execute(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    steps: &[StencilStep],
+) -> Result<Vec<StencilSnapshot>, String> {
+    let tracker = ApiCoverageTracker::global();
+    tracker.record(ApiCategory::Shader, "create_shader_module");
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Stencil Demo Shader"),
+        source: wgpu::ShaderSource::Wgsl(STENCIL_DEMO_SHADER.into()),
+    });
+    let value_pipeline = create_pipeline(device, &module, None);
+
+    let mut vertices: Vec<f32> = Vec::new();
+    let mut ranges = Vec::with_capacity(steps.len());
+    for step in steps {
+        let start = (vertices.len() / 6) as u32;
+        for position in step.shape.vertices() {
+            vertices.extend_from_slice(&position);
+            vertices.extend_from_slice(&step.color);
+        }
+        ranges.push(start..(vertices.len() / 6) as u32);
+    }
+    tracker.record(ApiCategory::Buffer, "create_buffer");
+    let vertex_buffer = wgpu::util::DeviceExt::create_buffer_init(
+        device,
+        &wgpu::util::BufferInitDescriptor {
+            label: Some("Stencil Demo Vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        },
+    );
+
+    let color = create_target(device, "Stencil Demo Color", COLOR_FORMAT);
+    let stencil = create_target(device, "Stencil Demo Stencil", STENCIL_FORMAT);
+    let color_view = color.create_view(&wgpu::TextureViewDescriptor::default());
+    let stencil_view = stencil.create_view(&wgpu::TextureViewDescriptor::default());
+
+    tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Stencil Demo Encoder"),
+    });
+    let [r, g, b, a] = CLEAR_COLOR.map(|c| c as f64);
+    let mut readbacks = Vec::with_capacity(steps.len());
+    for (i, (step, range)) in steps.iter().zip(ranges).enumerate() {
+        let (color_load, stencil_load) = if i == 0 {
+            (
+                wgpu::LoadOp::Clear(wgpu::Color { r, g, b, a }),
+                wgpu::LoadOp::Clear(0),
+            )
+        } else {
+            (wgpu::LoadOp::Load, wgpu::LoadOp::Load)
+        };
+        let pipeline = create_pipeline(device, &module, Some(step));
+        tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&step.name),
+                color_attachments: &[color_attachment(&color_view, color_load)],
+                depth_stencil_attachment: stencil_attachment(&stencil_view, stencil_load),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            pass.set_pipeline(&pipeline);
+            tracker.record(ApiCategory::RenderPass, "set_stencil_reference");
+            pass.set_stencil_reference(step.reference);
+            pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+            pass.draw(range, 0..1);
+        }
+
+        // Write every stencil value where the stencil holds it
+        let values = create_target(device, "Stencil Demo Values", VALUE_FORMAT);
+        let values_view = values.create_view(&wgpu::TextureViewDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Stencil Decode Pass"),
+                color_attachments: &[color_attachment(
+                    &values_view,
+                    wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                )],
+                depth_stencil_attachment: stencil_attachment(&stencil_view, wgpu::LoadOp::Load),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            pass.set_pipeline(&value_pipeline);
+            // Texels holding 0 keep the cleared value
+            for value in 1..=255u32 {
+                pass.set_stencil_reference(value);
+                pass.draw(0..3, value..value + 1);
+            }
+        }
+        readbacks.push((
+            copy_to_buffer(device, &mut encoder, &color, 4),
+            copy_to_buffer(device, &mut encoder, &values, 1),
+        ));
+    }
+    tracker.record(ApiCategory::Queue, "submit");
+    queue.submit(Some(encoder.finish()));
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    for ((color_buffer, _), (value_buffer, _)) in &readbacks {
+        for buffer in [color_buffer, value_buffer] {
+            let sender = sender.clone();
+            buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    let _ = sender.send(result);
+                });
+        }
+    }
+    drop(sender);
+    let _ = device.poll(wgpu::PollType::Wait {
+        submission_index: None,
+        timeout: None,
+    });
+    for result in receiver {
+        result.map_err(|e| e.to_string())?;
+    }
+
+    Ok(readbacks
+        .iter()
+        .map(
+            |((color_buffer, color_row), (value_buffer, value_row))| StencilSnapshot {
+                width: DEMO_SIZE,
+                height: DEMO_SIZE,
+                stencil: read_buffer(value_buffer, *value_row, DEMO_SIZE),
+                color: read_buffer(color_buffer, *color_row, DEMO_SIZE * 4),
+            },
+        )
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stencil_test_masks_both_sides() {
+        assert!(stencil_test(CompareFunction::Equal, 1, 1, 0xFF));
+        assert!(!stencil_test(CompareFunction::Equal, 1, 2, 0xFF));
+        // Only bit 0 is compared: 3 & 1 == 1 & 1
+        assert!(stencil_test(CompareFunction::Equal, 1, 3, 0x01));
+        // The reference is on the left: 1 < 2
+        assert!(stencil_test(CompareFunction::Less, 1, 2, 0xFF));
+        assert!(!stencil_test(CompareFunction::Greater, 1, 2, 0xFF));
+        assert!(!stencil_test(CompareFunction::Never, 0, 0, 0xFF));
+    }
+
+    #[test]
+    fn test_apply_op_clamps_wraps_and_masks() {
+        assert_eq!(
+            apply_op(StencilOperation::IncrementClamp, 255, 0, 0xFF),
+            255
+        );
+        assert_eq!(apply_op(StencilOperation::IncrementWrap, 255, 0, 0xFF), 0);
+        assert_eq!(apply_op(StencilOperation::DecrementClamp, 0, 0, 0xFF), 0);
+        assert_eq!(apply_op(StencilOperation::DecrementWrap, 0, 0, 0xFF), 255);
+        assert_eq!(
+            apply_op(StencilOperation::Invert, 0b1010, 0, 0xFF),
+            0b1111_0101
+        );
+        assert_eq!(apply_op(StencilOperation::Replace, 0, 0x1FF, 0xFF), 0xFF);
+        // Bits outside the write mask are kept
+        assert_eq!(
+            apply_op(StencilOperation::Replace, 0b1100, 0b0011, 0b0001),
+            0b1101
+        );
+        assert_eq!(apply_op(StencilOperation::Zero, 7, 0, 0xF0), 7);
+    }
+
+    #[test]
+    fn test_example_steps_on_cpu() {
+        let steps = example_steps();
+        let after = |covered: &[bool]| {
+            steps.iter().zip(covered).fold(
+                0,
+                |value, (step, &hit)| if hit { step.apply(value) } else { value },
+            )
+        };
+        // Circle and bar overlap: replaced with 1, then incremented
+        assert_eq!(after(&[true, true, true]), 2);
+        assert_eq!(after(&[false, true, true]), 1);
+        assert_eq!(after(&[false, false, true]), 0);
+        assert!(!steps[0].writes_color && steps[2].writes_color);
+    }
+
+    #[test]
+    fn test_shapes_face_the_camera() {
+        for shape in StencilShape::all() {
+            let vertices = shape.vertices();
+            assert_eq!(vertices.len() % 3, 0);
+            for triangle in vertices.chunks(3) {
+                let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+                let cross = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+                assert!(cross > 0.0, "{:?} has a clockwise triangle", shape);
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_histogram_and_overlay() {
+        let snapshot = StencilSnapshot {
+            width: 2,
+            height: 1,
+            stencil: vec![0, 2],
+            color: vec![10, 20, 30, 255, 0, 0, 0, 255],
+        };
+        assert_eq!(snapshot.histogram(), vec![(0, 1), (2, 1)]);
+        assert_eq!(snapshot.stencil_at(1, 0), Some(2));
+        assert_eq!(snapshot.stencil_at(2, 0), None);
+        let overlay = snapshot.overlay_rgba(1.0);
+        // Zero leaves the color alone, other values replace it at full opacity
+        assert_eq!(&overlay[..4], &[10, 20, 30, 255]);
+        assert_eq!(&overlay[4..], &stencil_color(2));
+    }
+}
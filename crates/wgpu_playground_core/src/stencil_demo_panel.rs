@@ -0,0 +1,343 @@
+use crate::render_pipeline::{CompareFunction, StencilFaceState, StencilOperation};
+use crate::stencil_demo::{
+    example_steps, execute, stencil_color, StencilShape, StencilSnapshot, StencilStep,
+};
+
+/// Presets for the linked step's face state
+const FACE_PRESETS: [(&str, CompareFunction, StencilOperation); 3] = [
+    (
+        "Inside mask",
+        CompareFunction::Equal,
+        StencilOperation::Keep,
+    ),
+    (
+        "Outside mask",
+        CompareFunction::NotEqual,
+        StencilOperation::Keep,
+    ),
+    (
+        "Count overdraw",
+        CompareFunction::Always,
+        StencilOperation::IncrementClamp,
+    ),
+];
+
+/// One-line summary of a face state, e.g. `Equal · fail Keep · pass Keep`
+pub fn face_summary(face: &StencilFaceState) -> String {
+    format!(
+        "{:?} · fail {:?} · pass {:?}",
+        face.compare, face.fail_op, face.pass_op
+    )
+}
+
+/// Steps through the stencil demo and shows the stencil buffer after each draw
+pub struct StencilDemoPanel {
+    steps: Vec<StencilStep>,
+    /// Step that takes its face state and masks from the pipeline panel
+    linked_step: Option<usize>,
+    /// Steps the snapshots were taken with
+    executed: Vec<StencilStep>,
+    snapshots: Result<Vec<StencilSnapshot>, String>,
+    /// Step whose snapshot is shown
+    current: usize,
+    /// Opacity of the stencil overlay over the color
+    opacity: f32,
+    zoom: f32,
+    image: Option<egui::TextureHandle>,
+    /// Step and opacity the image was built for
+    image_key: Option<(usize, f32)>,
+}
+
+impl Default for StencilDemoPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StencilDemoPanel {
+    pub fn new() -> Self {
+        let steps = example_steps();
+        Self {
+            linked_step: Some(steps.len() - 1),
+            current: steps.len() - 1,
+            steps,
+            executed: Vec::new(),
+            snapshots: Ok(Vec::new()),
+            opacity: 0.6,
+            zoom: 2.0,
+            image: None,
+            image_key: None,
+        }
+    }
+
+    pub fn steps(&self) -> &[StencilStep] {
+        &self.steps
+    }
+
+    pub fn linked_step(&self) -> Option<usize> {
+        self.linked_step
+    }
+
+    pub fn set_linked_step(&mut self, step: Option<usize>) {
+        self.linked_step = step.filter(|&i| i < self.steps.len());
+    }
+
+    /// The steps with the linked one using the pipeline panel's settings
+    pub fn effective_steps(
+        &self,
+        face: StencilFaceState,
+        read_mask: u32,
+        write_mask: u32,
+    ) -> Vec<StencilStep> {
+        let mut steps = self.steps.clone();
+        if let Some(step) = self.linked_step.and_then(|i| steps.get_mut(i)) {
+            step.face = face;
+            step.read_mask = read_mask;
+            step.write_mask = write_mask;
+        }
+        steps
+    }
+
+    /// Run the demo again if the steps changed since the last run
+    fn refresh(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, steps: Vec<StencilStep>) {
+        if steps == self.executed {
+            return;
+        }
+        self.snapshots = execute(device, queue, &steps);
+        self.executed = steps;
+        self.image_key = None;
+    }
+
+    /// Show the demo; `face` and the masks are the pipeline panel's stencil
+    /// front face settings, which the presets here may change
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        face: &mut StencilFaceState,
+        read_mask: u32,
+        write_mask: u32,
+    ) {
+        ui.label(
+            "Each draw tests the stencil buffer against its reference and updates it with the \
+            fail or pass operation. The overlay colors every texel by the value the stencil \
+            holds after the selected draw.",
+        );
+        ui.weak("The depth test always passes here, so the depth fail operation never runs.");
+        ui.add_space(5.0);
+
+        self.steps_ui(ui, face);
+        let steps = self.effective_steps(*face, read_mask, write_mask);
+        self.refresh(device, queue, steps);
+
+        let snapshots = match &self.snapshots {
+            Ok(snapshots) if !snapshots.is_empty() => snapshots,
+            Ok(_) => return,
+            Err(error) => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 50, 50),
+                    format!("❌ {}", error),
+                );
+                return;
+            }
+        };
+        self.current = self.current.min(snapshots.len() - 1);
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.current > 0, egui::Button::new("◀ Previous"))
+                .clicked()
+            {
+                self.current -= 1;
+            }
+            ui.label(format!(
+                "After draw {} of {}: {}",
+                self.current + 1,
+                snapshots.len(),
+                self.executed[self.current].name
+            ));
+            if ui
+                .add_enabled(
+                    self.current + 1 < snapshots.len(),
+                    egui::Button::new("Next ▶"),
+                )
+                .clicked()
+            {
+                self.current += 1;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut self.opacity, 0.0..=1.0).text("Overlay"));
+            ui.add(egui::Slider::new(&mut self.zoom, 1.0..=4.0).text("Zoom"));
+        });
+
+        let snapshot = &snapshots[self.current];
+        let key = (self.current, self.opacity);
+        if self.image_key != Some(key) || self.image.is_none() {
+            let image = egui::ColorImage::from_rgba_unmultiplied(
+                [snapshot.width as usize, snapshot.height as usize],
+                &snapshot.overlay_rgba(self.opacity),
+            );
+            self.image = Some(ui.ctx().load_texture(
+                "stencil_demo",
+                image,
+                egui::TextureOptions::NEAREST,
+            ));
+            self.image_key = Some(key);
+        }
+        let Some(image) = &self.image else {
+            return;
+        };
+
+        ui.horizontal_top(|ui| {
+            let size = egui::vec2(snapshot.width as f32, snapshot.height as f32) * self.zoom;
+            let response = ui.add(egui::Image::new(egui::load::SizedTexture::new(
+                image.id(),
+                size,
+            )));
+            if let Some(pos) = response.hover_pos() {
+                let texel = (pos - response.rect.min) / self.zoom;
+                let (x, y) = (texel.x as u32, texel.y as u32);
+                // The value at this texel after every draw so far
+                let history: Vec<String> = std::iter::once("0".to_string())
+                    .chain(
+                        snapshots[..=self.current]
+                            .iter()
+                            .filter_map(|s| s.stencil_at(x, y))
+                            .map(|v| v.to_string()),
+                    )
+                    .collect();
+                response.on_hover_text(format!("({}, {}) stencil {}", x, y, history.join(" → ")));
+            }
+
+            ui.vertical(|ui| {
+                ui.strong("Stencil values");
+                for (value, count) in snapshot.histogram() {
+                    ui.horizontal(|ui| {
+                        let [r, g, b, a] = stencil_color(value);
+                        let (rect, _) =
+                            ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                        let color = if a == 0 {
+                            egui::Color32::TRANSPARENT
+                        } else {
+                            egui::Color32::from_rgb(r, g, b)
+                        };
+                        ui.painter().rect_filled(rect, 2.0, color);
+                        ui.painter().rect_stroke(
+                            rect,
+                            2.0,
+                            egui::Stroke::new(1.0, egui::Color32::GRAY),
+                            egui::StrokeKind::Inside,
+                        );
+                        ui.monospace(format!("{:>3}: {} texels", value, count));
+                    });
+                }
+            });
+        });
+    }
+
+    fn steps_ui(&mut self, ui: &mut egui::Ui, face: &mut StencilFaceState) {
+        egui::Grid::new("stencil_demo_steps")
+            .num_columns(6)
+            .spacing([10.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Draw");
+                ui.strong("Shape");
+                ui.strong("Reference");
+                ui.strong("Color");
+                ui.strong("Face state");
+                ui.strong("Linked");
+                ui.end_row();
+
+                for (i, step) in self.steps.iter_mut().enumerate() {
+                    if ui
+                        .selectable_label(self.current == i, format!("{}. {}", i + 1, step.name))
+                        .clicked()
+                    {
+                        self.current = i;
+                    }
+                    egui::ComboBox::from_id_salt(("stencil_demo_shape", i))
+                        .selected_text(step.shape.name())
+                        .show_ui(ui, |ui| {
+                            for shape in StencilShape::all() {
+                                ui.selectable_value(&mut step.shape, shape, shape.name());
+                            }
+                        });
+                    ui.add(egui::DragValue::new(&mut step.reference).range(0..=255));
+                    ui.checkbox(&mut step.writes_color, "");
+                    let linked = self.linked_step == Some(i);
+                    if linked {
+                        ui.label(face_summary(face))
+                            .on_hover_text("The Stencil Front Face settings and masks above");
+                    } else {
+                        ui.label(face_summary(&step.face));
+                    }
+                    if ui
+                        .radio(linked, "")
+                        .on_hover_text("Use the pipeline's stencil settings for this draw")
+                        .clicked()
+                    {
+                        self.linked_step = if linked { None } else { Some(i) };
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if self.linked_step.is_some() {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Linked draw:");
+                for (name, compare, pass_op) in FACE_PRESETS {
+                    if ui.button(name).clicked() {
+                        face.compare = compare;
+                        face.fail_op = StencilOperation::Keep;
+                        face.depth_fail_op = StencilOperation::Keep;
+                        face.pass_op = pass_op;
+                    }
+                }
+            });
+        }
+        if ui.button("↺ Reset draws").clicked() {
+            let linked = self.linked_step;
+            *self = Self::new();
+            self.linked_step = linked.filter(|&i| i < self.steps.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stencil_demo::always;
+
+    #[test]
+    fn test_linked_step_takes_pipeline_settings() {
+        let mut panel = StencilDemoPanel::new();
+        let face = always(StencilOperation::Invert);
+
+        let steps = panel.effective_steps(face, 0x0F, 0x03);
+        let linked = panel.linked_step().unwrap();
+        assert_eq!(steps[linked].face, face);
+        assert_eq!(
+            (steps[linked].read_mask, steps[linked].write_mask),
+            (0x0F, 0x03)
+        );
+        assert_eq!(steps[0], panel.steps()[0]);
+
+        panel.set_linked_step(None);
+        assert_eq!(panel.effective_steps(face, 0, 0), panel.steps());
+        panel.set_linked_step(Some(10));
+        assert_eq!(panel.linked_step(), None);
+    }
+
+    #[test]
+    fn test_face_summary() {
+        let face = StencilFaceState {
+            compare: CompareFunction::Equal,
+            ..always(StencilOperation::Keep)
+        };
+        assert_eq!(face_summary(&face), "Equal · fail Keep · pass Keep");
+    }
+}
@@ -0,0 +1,391 @@
+//! Window surface settings and a gamma test overlay
+//!
+//! [`SurfaceSettings`] holds the parts of a `wgpu::SurfaceConfiguration`
+//! that can be picked from the surface's capabilities: format, present
+//! mode, alpha mode and view formats. [`GammaOverlay`] draws a test card
+//! straight into the surface texture to show what the format's sRGB-ness
+//! does to shader output: each row puts a one-pixel black and white
+//! checker, which averages to 50% linear light, next to a gray computed by
+//! a shader. The gray only matches the checker when the shader's output is
+//! encoded exactly once on the way to the display.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+
+/// Selectable parts of a surface configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceSettings {
+    pub format: wgpu::TextureFormat,
+    pub present_mode: wgpu::PresentMode,
+    pub alpha_mode: wgpu::CompositeAlphaMode,
+    pub view_formats: Vec<wgpu::TextureFormat>,
+}
+
+impl SurfaceSettings {
+    /// The settings a surface is configured with
+    pub fn from_config(config: &wgpu::SurfaceConfiguration) -> Self {
+        Self {
+            format: config.format,
+            present_mode: config.present_mode,
+            alpha_mode: config.alpha_mode,
+            view_formats: config.view_formats.clone(),
+        }
+    }
+
+    /// Copy the settings into a surface configuration
+    ///
+    /// Values the capabilities do not list keep the configuration's current
+    /// value, and view formats other than the format's sRGB counterpart are
+    /// dropped. Returns a message for each setting that was not applied.
+    pub fn apply_to(
+        &self,
+        config: &mut wgpu::SurfaceConfiguration,
+        capabilities: &wgpu::SurfaceCapabilities,
+    ) -> Vec<String> {
+        let mut rejected = Vec::new();
+        if capabilities.formats.contains(&self.format) {
+            config.format = self.format;
+        } else {
+            rejected.push(format!("Format {:?} is not supported", self.format));
+        }
+        if capabilities.present_modes.contains(&self.present_mode) {
+            config.present_mode = self.present_mode;
+        } else {
+            rejected.push(format!(
+                "Present mode {:?} is not supported",
+                self.present_mode
+            ));
+        }
+        if capabilities.alpha_modes.contains(&self.alpha_mode) {
+            config.alpha_mode = self.alpha_mode;
+        } else {
+            rejected.push(format!("Alpha mode {:?} is not supported", self.alpha_mode));
+        }
+
+        let counterpart = srgb_counterpart(config.format);
+        config.view_formats.clear();
+        for &view_format in &self.view_formats {
+            if Some(view_format) == counterpart {
+                config.view_formats.push(view_format);
+            } else if view_format != config.format {
+                rejected.push(format!(
+                    "View format {:?} differs from {:?} in more than sRGB-ness",
+                    view_format, config.format
+                ));
+            }
+        }
+        rejected
+    }
+}
+
+/// The format that differs from `format` only in sRGB-ness, if there is one
+///
+/// These are the only formats a surface texture may be viewed as.
+pub fn srgb_counterpart(format: wgpu::TextureFormat) -> Option<wgpu::TextureFormat> {
+    let counterpart = if format.is_srgb() {
+        format.remove_srgb_suffix()
+    } else {
+        format.add_srgb_suffix()
+    };
+    (counterpart != format).then_some(counterpart)
+}
+
+/// How writes to a target of `format` are encoded, for the UI
+pub fn format_encoding(format: wgpu::TextureFormat) -> &'static str {
+    if format.is_srgb() {
+        "sRGB: shader output is treated as linear and encoded on write"
+    } else {
+        "Linear: shader output is stored as is and shown as if already encoded"
+    }
+}
+
+/// Rows of the gamma overlay, top to bottom: name and what the shader does
+pub const OVERLAY_ROWS: [(&str, &str); 3] = [
+    (
+        "Correct",
+        "Writes linear light to sRGB targets and encodes it itself for linear targets",
+    ),
+    (
+        "Never encodes",
+        "Always writes linear light; too dark on linear targets",
+    ),
+    (
+        "Always encodes",
+        "Always applies the sRGB curve itself; encoded twice and too bright on sRGB targets",
+    ),
+];
+
+const OVERLAY_SHADER: &str = r#"
+// True when the render target encodes to sRGB on write
+override TARGET_SRGB: bool;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+fn srgb_encode(linear: f32) -> f32 {
+    if linear <= 0.0031308 {
+        return linear * 12.92;
+    }
+    return 1.055 * pow(linear, 1.0 / 2.4) - 0.055;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Black and white are the same in every encoding, so the checker is
+    // an exact 50% gray in linear light wherever it is shown
+    if in.uv.x < 0.5 {
+        let on = ((u32(in.position.x) + u32(in.position.y)) & 1u) == 1u;
+        let value = select(0.0, 1.0, on);
+        return vec4<f32>(value, value, value, 1.0);
+    }
+
+    let linear = 0.5;
+    let row = u32(in.uv.y * 3.0);
+    var value = linear;
+    if row == 0u {
+        value = select(srgb_encode(linear), linear, TARGET_SRGB);
+    } else if row == 2u {
+        value = srgb_encode(linear);
+    }
+    return vec4<f32>(value, value, value, 1.0);
+}
+"#;
+
+/// Draws the gamma test card into a region of a render target
+pub struct GammaOverlay {
+    pipeline: wgpu::RenderPipeline,
+    format: wgpu::TextureFormat,
+}
+
+impl GammaOverlay {
+    /// Create the pipeline for targets of `format`
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Gamma Overlay Shader"),
+            source: wgpu::ShaderSource::Wgsl(OVERLAY_SHADER.into()),
+        });
+
+        let constants = [("TARGET_SRGB", if format.is_srgb() { 1.0 } else { 0.0 })];
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Gamma Overlay Pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(format.into())],
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &constants,
+                    ..Default::default()
+                },
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Self { pipeline, format }
+    }
+
+    /// Target format the pipeline was created for
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// Draw the test card into `viewport` (x, y, width, height in pixels)
+    /// of a `target_size` sized `view`, clipped to `clip` and keeping what
+    /// is already there elsewhere
+    pub fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        target_size: [u32; 2],
+        viewport: [f32; 4],
+        clip: [f32; 4],
+    ) {
+        let Some([sx, sy, sw, sh]) = overlay_scissor(viewport, clip, target_size) else {
+            return;
+        };
+        let [x, y, width, height] = viewport;
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Gamma Overlay Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        pass.set_viewport(x, y, width, height, 0.0, 1.0);
+        pass.set_scissor_rect(sx, sy, sw, sh);
+        pass.set_pipeline(&self.pipeline);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+/// Scissor rect of the part of `viewport` inside both `clip` and the
+/// target, all as x, y, width, height in pixels
+///
+/// None if nothing of the viewport is visible.
+pub fn overlay_scissor(
+    viewport: [f32; 4],
+    clip: [f32; 4],
+    target_size: [u32; 2],
+) -> Option<[u32; 4]> {
+    let left = viewport[0].max(clip[0]).max(0.0).round();
+    let top = viewport[1].max(clip[1]).max(0.0).round();
+    let right = (viewport[0] + viewport[2])
+        .min(clip[0] + clip[2])
+        .min(target_size[0] as f32)
+        .round();
+    let bottom = (viewport[1] + viewport[3])
+        .min(clip[1] + clip[3])
+        .min(target_size[1] as f32)
+        .round();
+    if right <= left || bottom <= top {
+        return None;
+    }
+    Some([
+        left as u32,
+        top as u32,
+        (right - left) as u32,
+        (bottom - top) as u32,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities() -> wgpu::SurfaceCapabilities {
+        wgpu::SurfaceCapabilities {
+            formats: vec![
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+                wgpu::TextureFormat::Bgra8Unorm,
+            ],
+            present_modes: vec![wgpu::PresentMode::Fifo],
+            alpha_modes: vec![wgpu::CompositeAlphaMode::Opaque],
+            usages: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        }
+    }
+
+    fn config() -> wgpu::SurfaceConfiguration {
+        wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            width: 64,
+            height: 64,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        }
+    }
+
+    #[test]
+    fn test_srgb_counterpart() {
+        assert_eq!(
+            srgb_counterpart(wgpu::TextureFormat::Bgra8Unorm),
+            Some(wgpu::TextureFormat::Bgra8UnormSrgb)
+        );
+        assert_eq!(
+            srgb_counterpart(wgpu::TextureFormat::Rgba8UnormSrgb),
+            Some(wgpu::TextureFormat::Rgba8Unorm)
+        );
+        assert_eq!(srgb_counterpart(wgpu::TextureFormat::Rgba16Float), None);
+    }
+
+    #[test]
+    fn test_apply_supported_settings() {
+        let mut config = config();
+        let settings = SurfaceSettings {
+            format: wgpu::TextureFormat::Bgra8Unorm,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![wgpu::TextureFormat::Bgra8UnormSrgb],
+        };
+        assert!(settings.apply_to(&mut config, &capabilities()).is_empty());
+        assert_eq!(SurfaceSettings::from_config(&config), settings);
+    }
+
+    #[test]
+    fn test_apply_rejects_unsupported_settings() {
+        let mut config = config();
+        let settings = SurfaceSettings {
+            format: wgpu::TextureFormat::Rgba16Float,
+            present_mode: wgpu::PresentMode::Immediate,
+            alpha_mode: wgpu::CompositeAlphaMode::PreMultiplied,
+            view_formats: vec![
+                wgpu::TextureFormat::Bgra8Unorm,
+                wgpu::TextureFormat::Rgba8Unorm,
+            ],
+        };
+        let rejected = settings.apply_to(&mut config, &capabilities());
+        assert_eq!(rejected.len(), 4);
+        assert_eq!(config.format, wgpu::TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(config.present_mode, wgpu::PresentMode::Fifo);
+        // The counterpart of the kept format is still allowed
+        assert_eq!(config.view_formats, vec![wgpu::TextureFormat::Bgra8Unorm]);
+    }
+
+    #[test]
+    fn test_overlay_scissor() {
+        let viewport = [10.0, 20.0, 100.0, 50.0];
+        assert_eq!(
+            overlay_scissor(viewport, [0.0, 0.0, 1000.0, 1000.0], [800, 600]),
+            Some([10, 20, 100, 50])
+        );
+        // Scrolled halfway out of the clip rect
+        assert_eq!(
+            overlay_scissor(viewport, [0.0, 45.0, 1000.0, 1000.0], [800, 600]),
+            Some([10, 45, 100, 25])
+        );
+        // Partly off the target
+        assert_eq!(
+            overlay_scissor(viewport, [0.0, 0.0, 1000.0, 1000.0], [50, 600]),
+            Some([10, 20, 40, 50])
+        );
+        assert_eq!(
+            overlay_scissor(viewport, [200.0, 0.0, 10.0, 10.0], [800, 600]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_overlay_shader_validates() {
+        let module = naga::front::wgsl::parse_str(OVERLAY_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+}
@@ -0,0 +1,400 @@
+use egui::{Color32, Pos2, Rect, Vec2};
+
+use crate::frame_pacing::present_mode_description;
+use crate::surface_config::{format_encoding, srgb_counterpart, SurfaceSettings, OVERLAY_ROWS};
+
+/// Size of the gamma test card in points
+const CARD_SIZE: Vec2 = Vec2::new(240.0, 150.0);
+/// Width of the row labels left of the card
+const LABEL_WIDTH: f32 = 130.0;
+
+/// Where to draw the gamma test card this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayTarget {
+    /// Card rect in points
+    pub rect: Rect,
+    /// Visible part of the panel in points
+    pub clip: Rect,
+    /// Format of the surface view to draw through
+    pub format: wgpu::TextureFormat,
+}
+
+/// UI panel that lists the window surface's capabilities, switches its
+/// format, present mode, alpha mode and view formats at runtime and shows a
+/// gamma test card drawn straight into the surface
+pub struct SurfaceConfigPanel {
+    capabilities: Option<wgpu::SurfaceCapabilities>,
+    /// Whether the adapter allows surface view formats
+    view_formats_supported: bool,
+    /// Settings chosen in the UI
+    settings: Option<SurfaceSettings>,
+    /// Settings the surface is configured with
+    active: Option<SurfaceSettings>,
+    apply_requested: bool,
+    /// Settings from the last apply that the surface could not take
+    rejected: Vec<String>,
+    show_overlay: bool,
+    /// Draw the card through the sRGB counterpart view instead of the
+    /// surface format
+    overlay_through_view: bool,
+    overlay_target: Option<OverlayTarget>,
+}
+
+impl Default for SurfaceConfigPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SurfaceConfigPanel {
+    pub fn new() -> Self {
+        Self {
+            capabilities: None,
+            view_formats_supported: false,
+            settings: None,
+            active: None,
+            apply_requested: false,
+            rejected: Vec::new(),
+            show_overlay: true,
+            overlay_through_view: false,
+            overlay_target: None,
+        }
+    }
+
+    /// Record how the surface is configured and what it supports
+    pub fn set_active(
+        &mut self,
+        config: &wgpu::SurfaceConfiguration,
+        capabilities: &wgpu::SurfaceCapabilities,
+        view_formats_supported: bool,
+    ) {
+        let settings = SurfaceSettings::from_config(config);
+        self.settings = Some(settings.clone());
+        self.active = Some(settings);
+        self.capabilities = Some(capabilities.clone());
+        self.view_formats_supported = view_formats_supported;
+    }
+
+    pub fn active(&self) -> Option<&SurfaceSettings> {
+        self.active.as_ref()
+    }
+
+    /// Select new settings; they are applied to the surface before the next
+    /// frame
+    pub fn set_settings(&mut self, settings: SurfaceSettings) {
+        if self.settings.as_ref() != Some(&settings) {
+            self.settings = Some(settings);
+            self.apply_requested = true;
+        }
+    }
+
+    /// Returns the settings to reconfigure the surface with, once per change
+    pub fn take_apply_request(&mut self) -> Option<SurfaceSettings> {
+        if std::mem::take(&mut self.apply_requested) {
+            self.settings.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Report the settings the surface could not take in the last apply
+    pub fn report_rejected(&mut self, rejected: Vec<String>) {
+        self.rejected = rejected;
+    }
+
+    /// Where the render loop should draw the gamma test card, if the panel
+    /// was shown this frame
+    pub fn take_overlay_target(&mut self) -> Option<OverlayTarget> {
+        self.overlay_target.take()
+    }
+
+    /// Format the test card is drawn with
+    fn overlay_format(&self, active: &SurfaceSettings) -> wgpu::TextureFormat {
+        match srgb_counterpart(active.format) {
+            Some(view) if self.overlay_through_view && active.view_formats.contains(&view) => view,
+            _ => active.format,
+        }
+    }
+
+    /// Render the panel
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🖼 Surface Configuration");
+        ui.separator();
+        ui.label(
+            "The window surface is configured from what get_capabilities reports for this \
+             adapter. Switch the format, present mode, alpha mode and view formats while the \
+             app runs.",
+        );
+        ui.add_space(10.0);
+
+        let (Some(capabilities), Some(active), Some(mut settings)) = (
+            self.capabilities.clone(),
+            self.active.clone(),
+            self.settings.clone(),
+        ) else {
+            ui.label("The surface has not been configured yet.");
+            return;
+        };
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.label(egui::RichText::new("Capabilities").strong());
+            egui::Grid::new("surface_capabilities")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Formats:");
+                    ui.vertical(|ui| {
+                        for format in &capabilities.formats {
+                            let encoding = if format.is_srgb() { "sRGB" } else { "linear" };
+                            ui.monospace(format!("{:?} ({})", format, encoding));
+                        }
+                    });
+                    ui.end_row();
+                    ui.label("Present modes:");
+                    ui.monospace(format!("{:?}", capabilities.present_modes));
+                    ui.end_row();
+                    ui.label("Alpha modes:");
+                    ui.monospace(format!("{:?}", capabilities.alpha_modes));
+                    ui.end_row();
+                    ui.label("Usages:");
+                    ui.monospace(format!("{:?}", capabilities.usages));
+                    ui.end_row();
+                });
+            ui.add_space(10.0);
+
+            ui.label(egui::RichText::new("Configuration").strong());
+            egui::Grid::new("surface_settings")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Format:");
+                    egui::ComboBox::from_id_salt("surface_format")
+                        .selected_text(format!("{:?}", settings.format))
+                        .show_ui(ui, |ui| {
+                            for &format in &capabilities.formats {
+                                ui.selectable_value(
+                                    &mut settings.format,
+                                    format,
+                                    format!("{:?}", format),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Present mode:");
+                    egui::ComboBox::from_id_salt("surface_present_mode")
+                        .selected_text(format!("{:?}", settings.present_mode))
+                        .show_ui(ui, |ui| {
+                            for &mode in &capabilities.present_modes {
+                                ui.selectable_value(
+                                    &mut settings.present_mode,
+                                    mode,
+                                    format!("{:?}", mode),
+                                )
+                                .on_hover_text(present_mode_description(mode));
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Alpha mode:");
+                    egui::ComboBox::from_id_salt("surface_alpha_mode")
+                        .selected_text(format!("{:?}", settings.alpha_mode))
+                        .show_ui(ui, |ui| {
+                            for &mode in &capabilities.alpha_modes {
+                                ui.selectable_value(
+                                    &mut settings.alpha_mode,
+                                    mode,
+                                    format!("{:?}", mode),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("View formats:");
+                    match srgb_counterpart(settings.format) {
+                        Some(counterpart) => {
+                            let mut enabled = settings.view_formats.contains(&counterpart);
+                            let response = ui
+                                .add_enabled(
+                                    self.view_formats_supported,
+                                    egui::Checkbox::new(
+                                        &mut enabled,
+                                        format!("{:?}", counterpart),
+                                    ),
+                                )
+                                .on_hover_text(
+                                    "Allow views of the surface texture in the format's sRGB or linear counterpart",
+                                )
+                                .on_disabled_hover_text(
+                                    "The adapter does not support surface view formats",
+                                );
+                            if response.changed() {
+                                settings.view_formats = if enabled {
+                                    vec![counterpart]
+                                } else {
+                                    Vec::new()
+                                };
+                            }
+                        }
+                        None => {
+                            ui.label("None: the format has no sRGB counterpart");
+                        }
+                    }
+                    ui.end_row();
+                });
+            // A counterpart of the previous format is not a valid view format
+            // of a new one
+            settings
+                .view_formats
+                .retain(|&view| Some(view) == srgb_counterpart(settings.format));
+
+            ui.label(
+                egui::RichText::new(format_encoding(active.format))
+                    .small()
+                    .weak(),
+            );
+            if self.settings.as_ref() != Some(&settings) || active != settings {
+                ui.label(
+                    egui::RichText::new("Applying to the surface...")
+                        .small()
+                        .weak(),
+                );
+            }
+            for message in &self.rejected {
+                ui.colored_label(Color32::YELLOW, format!("⚠ {}", message));
+            }
+            ui.add_space(10.0);
+
+            self.render_overlay(ui, &active);
+        });
+
+        self.set_settings(settings);
+    }
+
+    fn render_overlay(&mut self, ui: &mut egui::Ui, active: &SurfaceSettings) {
+        ui.label(egui::RichText::new("Gamma Test Card").strong());
+        ui.checkbox(&mut self.show_overlay, "Show test card");
+        if !self.show_overlay {
+            return;
+        }
+        ui.label(
+            "Each row puts a black and white checker, which is 50% light, next to a gray a \
+             shader computed. Look from a distance: the gray matches the checker only where \
+             the value is encoded to sRGB exactly once.",
+        );
+        if let Some(view) = srgb_counterpart(active.format) {
+            ui.add_enabled(
+                active.view_formats.contains(&view),
+                egui::Checkbox::new(
+                    &mut self.overlay_through_view,
+                    format!("Draw through a {:?} view", view),
+                ),
+            )
+            .on_disabled_hover_text(format!(
+                "Add {:?} to the view formats to draw through it",
+                view
+            ));
+        }
+        let format = self.overlay_format(active);
+        ui.label(format!("Drawing to {:?}", format));
+
+        let (rect, _) = ui.allocate_exact_size(
+            Vec2::new(LABEL_WIDTH + CARD_SIZE.x, CARD_SIZE.y),
+            egui::Sense::hover(),
+        );
+        let card = Rect::from_min_size(Pos2::new(rect.left() + LABEL_WIDTH, rect.top()), CARD_SIZE);
+        let painter = ui.painter_at(rect);
+        let row_height = CARD_SIZE.y / OVERLAY_ROWS.len() as f32;
+        for (row, (name, _)) in OVERLAY_ROWS.iter().enumerate() {
+            painter.text(
+                Pos2::new(rect.left(), rect.top() + row_height * (row as f32 + 0.5)),
+                egui::Align2::LEFT_CENTER,
+                *name,
+                egui::FontId::proportional(13.0),
+                ui.visuals().text_color(),
+            );
+        }
+        self.overlay_target = Some(OverlayTarget {
+            rect: card,
+            clip: ui.clip_rect(),
+            format,
+        });
+
+        ui.add_space(5.0);
+        for (name, description) in OVERLAY_ROWS {
+            ui.label(
+                egui::RichText::new(format!("{}: {}", name, description))
+                    .small()
+                    .weak(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> wgpu::SurfaceConfiguration {
+        wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: wgpu::TextureFormat::Bgra8Unorm,
+            width: 64,
+            height: 64,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![wgpu::TextureFormat::Bgra8UnormSrgb],
+            desired_maximum_frame_latency: 2,
+        }
+    }
+
+    fn capabilities() -> wgpu::SurfaceCapabilities {
+        wgpu::SurfaceCapabilities {
+            formats: vec![
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+                wgpu::TextureFormat::Bgra8Unorm,
+            ],
+            present_modes: vec![wgpu::PresentMode::Fifo],
+            alpha_modes: vec![wgpu::CompositeAlphaMode::Opaque],
+            usages: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        }
+    }
+
+    #[test]
+    fn test_changed_settings_request_apply_once() {
+        let mut panel = SurfaceConfigPanel::new();
+        assert_eq!(panel.take_apply_request(), None);
+        panel.set_active(&config(), &capabilities(), true);
+
+        let mut settings = panel.active().unwrap().clone();
+        panel.set_settings(settings.clone());
+        assert_eq!(panel.take_apply_request(), None);
+
+        settings.format = wgpu::TextureFormat::Bgra8UnormSrgb;
+        panel.set_settings(settings.clone());
+        assert_eq!(panel.take_apply_request(), Some(settings));
+        assert_eq!(panel.take_apply_request(), None);
+    }
+
+    #[test]
+    fn test_overlay_format_uses_view_only_when_configured() {
+        let mut panel = SurfaceConfigPanel::new();
+        let mut active = SurfaceSettings::from_config(&config());
+        assert_eq!(
+            panel.overlay_format(&active),
+            wgpu::TextureFormat::Bgra8Unorm
+        );
+        panel.overlay_through_view = true;
+        assert_eq!(
+            panel.overlay_format(&active),
+            wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+        active.view_formats.clear();
+        assert_eq!(
+            panel.overlay_format(&active),
+            wgpu::TextureFormat::Bgra8Unorm
+        );
+        assert!(panel.take_overlay_target().is_none());
+    }
+}
@@ -0,0 +1,181 @@
+//! Rasterizing SVG images into textures
+//!
+//! An SVG has no pixels of its own, so it is drawn with resvg at whatever
+//! size the texture should have: its intrinsic size (the `width` and
+//! `height` of the root element) when it is first loaded, any other size
+//! when the Textures tab re-rasterizes it. The result is plain RGBA, ready
+//! for the same upload path as decoded PNG and JPEG images.
+//!
+//! Text is drawn only with fonts embedded as paths; no system fonts are
+//! loaded.
+
+use std::fmt;
+
+use resvg::{tiny_skia, usvg};
+
+use crate::asset_loader::DecodedImage;
+
+/// Largest width or height an SVG is rasterized at
+pub const MAX_RASTER_SIZE: u32 = 8192;
+
+/// Why an SVG couldn't be rasterized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SvgError {
+    /// The data isn't a valid SVG document
+    Parse(String),
+    /// The requested size is zero or above [`MAX_RASTER_SIZE`]
+    InvalidSize(u32, u32),
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::Parse(e) => write!(f, "invalid SVG: {}", e),
+            SvgError::InvalidSize(width, height) => write!(
+                f,
+                "can't rasterize at {}x{}: sizes must be 1 to {}",
+                width, height, MAX_RASTER_SIZE
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+/// Whether `bytes` look like an SVG document
+///
+/// SVG is XML without a magic number, so this looks for an `<svg` tag near
+/// the start of a text file.
+pub fn is_svg(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(4096)];
+    let head = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+    let start = head.iter().position(|b| !b.is_ascii_whitespace());
+    start.is_some_and(|start| head[start] == b'<')
+        && head.windows(4).any(|window| window == b"<svg")
+}
+
+fn parse(bytes: &[u8]) -> Result<usvg::Tree, SvgError> {
+    usvg::Tree::from_data(bytes, &usvg::Options::default())
+        .map_err(|e| SvgError::Parse(e.to_string()))
+}
+
+/// Width and height the SVG declares, in CSS pixels
+pub fn intrinsic_size(bytes: &[u8]) -> Result<(f32, f32), SvgError> {
+    let size = parse(bytes)?.size();
+    Ok((size.width(), size.height()))
+}
+
+/// Pixel size closest to `size`, clamped to what can be rasterized
+pub fn raster_size((width, height): (f32, f32)) -> (u32, u32) {
+    let pixels = |length: f32| (length.round() as u32).clamp(1, MAX_RASTER_SIZE);
+    (pixels(width), pixels(height))
+}
+
+/// `width` and the height that keeps the aspect ratio of `intrinsic`
+pub fn height_for_width(intrinsic: (f32, f32), width: u32) -> u32 {
+    let height = width as f32 * intrinsic.1 / intrinsic.0.max(f32::EPSILON);
+    (height.round() as u32).clamp(1, MAX_RASTER_SIZE)
+}
+
+/// `height` and the width that keeps the aspect ratio of `intrinsic`
+pub fn width_for_height(intrinsic: (f32, f32), height: u32) -> u32 {
+    let width = height as f32 * intrinsic.0 / intrinsic.1.max(f32::EPSILON);
+    (width.round() as u32).clamp(1, MAX_RASTER_SIZE)
+}
+
+/// Rasterize the SVG at its intrinsic size
+pub fn rasterize_intrinsic(bytes: &[u8]) -> Result<DecodedImage, SvgError> {
+    let tree = parse(bytes)?;
+    let size = tree.size();
+    let (width, height) = raster_size((size.width(), size.height()));
+    render(&tree, width, height)
+}
+
+/// Rasterize the SVG stretched to `width` by `height` pixels
+pub fn rasterize(bytes: &[u8], width: u32, height: u32) -> Result<DecodedImage, SvgError> {
+    render(&parse(bytes)?, width, height)
+}
+
+fn render(tree: &usvg::Tree, width: u32, height: u32) -> Result<DecodedImage, SvgError> {
+    if width > MAX_RASTER_SIZE || height > MAX_RASTER_SIZE {
+        return Err(SvgError::InvalidSize(width, height));
+    }
+    let mut pixmap =
+        tiny_skia::Pixmap::new(width, height).ok_or(SvgError::InvalidSize(width, height))?;
+    let size = tree.size();
+    let transform = tiny_skia::Transform::from_scale(
+        width as f32 / size.width(),
+        height as f32 / size.height(),
+    );
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    // tiny-skia stores premultiplied alpha; textures take straight alpha
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Ok(DecodedImage {
+        width,
+        height,
+        rgba,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARES: &[u8] = br##"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20" viewBox="0 0 4 2">
+  <rect x="0" y="0" width="2" height="2" fill="#ff0000"/>
+  <rect x="2" y="0" width="2" height="2" fill="#0000ff" fill-opacity="0.5"/>
+</svg>"##;
+
+    fn pixel(image: &DecodedImage, x: u32, y: u32) -> [u8; 4] {
+        let offset = 4 * (y * image.width + x) as usize;
+        image.rgba[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_detects_svg() {
+        assert!(is_svg(SQUARES));
+        assert!(is_svg(b"\xEF\xBB\xBF\n  <svg width=\"1\" height=\"1\"/>"));
+        assert!(!is_svg(b"\x89PNG\r\n\x1A\n"));
+        assert!(!is_svg(b"plain text mentioning <svg"));
+    }
+
+    #[test]
+    fn test_rasterize_at_intrinsic_and_chosen_size() {
+        assert_eq!(intrinsic_size(SQUARES), Ok((40.0, 20.0)));
+
+        let image = rasterize_intrinsic(SQUARES).unwrap();
+        assert_eq!((image.width, image.height), (40, 20));
+        assert_eq!(pixel(&image, 5, 10), [255, 0, 0, 255]);
+        // Straight alpha: the color stays blue at half coverage
+        let blue = pixel(&image, 35, 10);
+        assert_eq!((blue[0], blue[1], blue[2]), (0, 0, 255));
+        assert!((126..=129).contains(&blue[3]));
+
+        let large = rasterize(SQUARES, 400, 200).unwrap();
+        assert_eq!(large.rgba.len(), 400 * 200 * 4);
+        assert_eq!(pixel(&large, 50, 100), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_sizes_and_errors() {
+        assert_eq!(raster_size((12.4, 0.2)), (12, 1));
+        assert_eq!(raster_size((1.0e6, 10.0)), (MAX_RASTER_SIZE, 10));
+        assert_eq!(height_for_width((40.0, 20.0), 100), 50);
+        assert_eq!(width_for_height((40.0, 20.0), 100), 200);
+
+        assert_eq!(rasterize(SQUARES, 0, 10), Err(SvgError::InvalidSize(0, 10)));
+        assert!(matches!(
+            rasterize(b"<svg", 10, 10),
+            Err(SvgError::Parse(_))
+        ));
+    }
+}
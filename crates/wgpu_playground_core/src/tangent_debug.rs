@@ -0,0 +1,327 @@
+//! Debug views of normals and tangent frames on the model preview
+//!
+//! [`PbrRenderer`](crate::material::PbrRenderer) can color each pixel by its
+//! normal, tangent, bitangent or normal-mapped normal instead of shading it,
+//! and draw line glyphs along the tangent frame of the mesh vertices. The
+//! shader builds its frame per pixel from screen-space derivatives of the
+//! UVs; [`vertex_tangents`] computes the per-vertex tangents a pipeline with
+//! a tangent attribute would use, from the same UVs and with the same
+//! convention: the tangent points along +u and the bitangent along -v, as in
+//! glTF. Where the glyphs and the colors disagree, the UVs are mirrored or
+//! degenerate, or a normal map was authored for another convention.
+
+use crate::math_utils::{cross, dot, normalize};
+use crate::model_loader::{ModelData, ModelVertex};
+
+/// Most vertices that get glyphs; larger meshes show an even subset
+pub const MAX_GLYPHS: usize = 4096;
+
+/// What the model preview draws for each pixel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugView {
+    /// Lit with the materials, as normal
+    #[default]
+    Shaded,
+    /// Interpolated vertex normals
+    Normals,
+    Tangents,
+    Bitangents,
+    /// Normals after applying the normal map
+    MappedNormals,
+}
+
+impl DebugView {
+    pub const ALL: [DebugView; 5] = [
+        DebugView::Shaded,
+        DebugView::Normals,
+        DebugView::Tangents,
+        DebugView::Bitangents,
+        DebugView::MappedNormals,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DebugView::Shaded => "Shaded",
+            DebugView::Normals => "Normals",
+            DebugView::Tangents => "Tangents",
+            DebugView::Bitangents => "Bitangents",
+            DebugView::MappedNormals => "Normal-mapped normals",
+        }
+    }
+
+    /// Value of the shader's `*_VIEW` constant
+    pub fn shader_value(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Debug settings of the model preview
+#[derive(Debug, Clone, PartialEq)]
+pub struct TangentDebug {
+    pub view: DebugView,
+    /// Draw lines along the tangent frame of the vertices
+    pub glyphs: bool,
+    /// Length of the glyph lines, as a fraction of the model's radius
+    pub glyph_length: f32,
+    /// Multiplies the normal scale of every material
+    pub normal_map_strength: f32,
+}
+
+impl Default for TangentDebug {
+    fn default() -> Self {
+        Self {
+            view: DebugView::Shaded,
+            glyphs: false,
+            glyph_length: 0.05,
+            normal_map_strength: 1.0,
+        }
+    }
+}
+
+impl TangentDebug {
+    /// The shader's `debug` vector: view, normal map strength and glyph
+    /// length
+    pub fn uniforms(&self) -> [f32; 4] {
+        [
+            self.view.shader_value() as f32,
+            self.normal_map_strength,
+            self.glyph_length,
+            0.0,
+        ]
+    }
+
+    /// View picker, glyph toggle and strength sliders
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("View")
+            .selected_text(self.view.name())
+            .show_ui(ui, |ui| {
+                for view in DebugView::ALL {
+                    ui.selectable_value(&mut self.view, view, view.name());
+                }
+            });
+        if self.view != DebugView::Shaded {
+            ui.weak("World-space directions as colors: x red, y green, z blue");
+        }
+        ui.checkbox(&mut self.glyphs, "Tangent frame glyphs")
+            .on_hover_text(
+                "Lines along each vertex's tangent (red), bitangent (green) and normal (blue)",
+            );
+        ui.add_enabled(
+            self.glyphs,
+            egui::Slider::new(&mut self.glyph_length, 0.01..=0.25).text("Glyph length"),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.normal_map_strength, 0.0..=4.0).text("Normal map strength"),
+        );
+    }
+}
+
+/// Any unit vector perpendicular to `n`, the shader's fallback tangent
+fn perpendicular(n: [f32; 3]) -> [f32; 3] {
+    let axis = if n[0].abs() > 0.9 {
+        [0.0, 1.0, 0.0]
+    } else {
+        [1.0, 0.0, 0.0]
+    };
+    normalize(cross(n, axis))
+}
+
+/// Tangent of each vertex of an indexed triangle list, with the sign of its
+/// bitangent in w
+///
+/// The bitangent is `cross(normal, tangent) * w`; w is -1 where the UVs are
+/// mirrored. Vertices without usable UVs get an arbitrary perpendicular.
+pub fn vertex_tangents(vertices: &[ModelVertex], indices: &[u32]) -> Vec<[f32; 4]> {
+    let mut along_u = vec![[0.0f32; 3]; vertices.len()];
+    let mut along_v = vec![[0.0f32; 3]; vertices.len()];
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| triangle[i] as usize);
+        if a.max(b).max(c) >= vertices.len() {
+            continue;
+        }
+        let (p0, p1, p2) = (
+            vertices[a].position,
+            vertices[b].position,
+            vertices[c].position,
+        );
+        let (t0, t1, t2) = (
+            vertices[a].tex_coords,
+            vertices[b].tex_coords,
+            vertices[c].tex_coords,
+        );
+        let e1: [f32; 3] = std::array::from_fn(|i| p1[i] - p0[i]);
+        let e2: [f32; 3] = std::array::from_fn(|i| p2[i] - p0[i]);
+        let (du1, dv1) = (t1[0] - t0[0], t1[1] - t0[1]);
+        let (du2, dv2) = (t2[0] - t0[0], t2[1] - t0[1]);
+        let det = du1 * dv2 - du2 * dv1;
+        if det.abs() < 1e-12 {
+            continue;
+        }
+        // Derivatives of the position with respect to u and v
+        let dp_du: [f32; 3] = std::array::from_fn(|i| (e1[i] * dv2 - e2[i] * dv1) / det);
+        let dp_dv: [f32; 3] = std::array::from_fn(|i| (e2[i] * du1 - e1[i] * du2) / det);
+        for vertex in [a, b, c] {
+            for i in 0..3 {
+                along_u[vertex][i] += dp_du[i];
+                along_v[vertex][i] += dp_dv[i];
+            }
+        }
+    }
+
+    vertices
+        .iter()
+        .zip(along_u.iter().zip(&along_v))
+        .map(|(vertex, (&du, &dv))| {
+            let n = normalize(vertex.normal);
+            // Gram-Schmidt: the part of dP/du perpendicular to the normal
+            let t: [f32; 3] = std::array::from_fn(|i| du[i] - n[i] * dot(n, du));
+            if dot(t, t) < 1e-12 {
+                let t = perpendicular(n);
+                return [t[0], t[1], t[2], 1.0];
+            }
+            let t = normalize(t);
+            // The bitangent points against dP/dv unless the UVs are mirrored
+            let w = if dot(cross(n, t), dv) > 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            [t[0], t[1], t[2], w]
+        })
+        .collect()
+}
+
+/// Vertex of a glyph line
+///
+/// The line runs from `position` towards `position + offset`, scaled by the
+/// glyph length in the shader so the slider needs no new geometry.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GlyphVertex {
+    pub position: [f32; 3],
+    /// Zero at the start of a line, the direction times the model's radius
+    /// at its end
+    pub offset: [f32; 3],
+    pub color: [f32; 3],
+}
+
+/// Line list with the tangent (red), bitangent (green) and normal (blue) of
+/// up to [`MAX_GLYPHS`] vertices of `model`, each `radius` long before the
+/// glyph length is applied
+pub fn glyph_lines(model: &ModelData, radius: f32) -> Vec<GlyphVertex> {
+    let total: usize = model.meshes.iter().map(|mesh| mesh.vertices.len()).sum();
+    let stride = total.div_ceil(MAX_GLYPHS).max(1);
+    let mut lines = Vec::with_capacity(total.min(MAX_GLYPHS) * 6);
+    let mut index = 0;
+    for mesh in &model.meshes {
+        let tangents = vertex_tangents(&mesh.vertices, &mesh.indices);
+        for (vertex, tangent) in mesh.vertices.iter().zip(tangents) {
+            index += 1;
+            if (index - 1) % stride != 0 {
+                continue;
+            }
+            let n = normalize(vertex.normal);
+            let t = [tangent[0], tangent[1], tangent[2]];
+            let b = cross(n, t).map(|c| c * tangent[3]);
+            for (direction, color) in [
+                (t, [1.0, 0.0, 0.0]),
+                (b, [0.0, 1.0, 0.0]),
+                (n, [0.0, 0.0, 1.0]),
+            ] {
+                lines.push(GlyphVertex {
+                    position: vertex.position,
+                    offset: [0.0; 3],
+                    color,
+                });
+                lines.push(GlyphVertex {
+                    position: vertex.position,
+                    offset: direction.map(|c| c * radius),
+                    color,
+                });
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_loader::Mesh;
+
+    /// A quad in the XY plane facing +Z, v running down the image
+    fn quad(mirror_u: bool) -> Vec<ModelVertex> {
+        let u = |u: f32| if mirror_u { 1.0 - u } else { u };
+        let normal = [0.0, 0.0, 1.0];
+        vec![
+            ModelVertex::new([-1.0, -1.0, 0.0], normal, [u(0.0), 1.0]),
+            ModelVertex::new([1.0, -1.0, 0.0], normal, [u(1.0), 1.0]),
+            ModelVertex::new([1.0, 1.0, 0.0], normal, [u(1.0), 0.0]),
+            ModelVertex::new([-1.0, 1.0, 0.0], normal, [u(0.0), 0.0]),
+        ]
+    }
+
+    const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+    fn assert_near(a: [f32; 4], b: [f32; 4]) {
+        assert!(
+            a.iter().zip(&b).all(|(a, b)| (a - b).abs() < 1e-5),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn test_vertex_tangents_follow_uvs() {
+        for tangent in vertex_tangents(&quad(false), &QUAD_INDICES) {
+            // Tangent along +u (+X), bitangent cross(Z, X) = +Y against +v
+            assert_near(tangent, [1.0, 0.0, 0.0, 1.0]);
+        }
+        for tangent in vertex_tangents(&quad(true), &QUAD_INDICES) {
+            // +u runs along -X; the bitangent still points up
+            assert_near(tangent, [-1.0, 0.0, 0.0, -1.0]);
+        }
+
+        // Without UVs any perpendicular tangent will do
+        let mut flat = quad(false);
+        for vertex in &mut flat {
+            vertex.tex_coords = [0.0, 0.0];
+        }
+        for tangent in vertex_tangents(&flat, &QUAD_INDICES) {
+            assert!(dot([tangent[0], tangent[1], tangent[2]], [0.0, 0.0, 1.0]).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_glyph_lines() {
+        let model = ModelData {
+            meshes: vec![Mesh {
+                vertices: quad(false),
+                indices: QUAD_INDICES.to_vec(),
+                material_index: None,
+            }],
+            materials: Vec::new(),
+            textures: Vec::new(),
+            vertex_count: 4,
+            index_count: 6,
+        };
+        let lines = glyph_lines(&model, 2.0);
+        assert_eq!(lines.len(), 4 * 3 * 2);
+        assert_eq!(lines[0].offset, [0.0; 3]);
+        assert_eq!(lines[1].offset, [2.0, 0.0, 0.0]);
+        assert_eq!(lines[3].offset, [0.0, 2.0, 0.0]);
+        assert_eq!(lines[5].offset, [0.0, 0.0, 2.0]);
+        assert_eq!(lines[5].color, [0.0, 0.0, 1.0]);
+
+        let dense = ModelData {
+            meshes: vec![Mesh {
+                vertices: vec![quad(false)[0]; MAX_GLYPHS * 2 + 1],
+                indices: Vec::new(),
+                material_index: None,
+            }],
+            ..model
+        };
+        assert!(glyph_lines(&dense, 1.0).len() <= MAX_GLYPHS * 6);
+    }
+}
@@ -0,0 +1,814 @@
+//! Procedural terrain drawn as a clipmap-style LOD grid
+//!
+//! A compute pass sums octaves of gradient noise into an `r32float`
+//! heightmap whenever the noise parameters change. The terrain itself has
+//! no vertex or index buffers: every level of detail is a square of
+//! `GRID_CELLS`x`GRID_CELLS` cells around the camera, twice as coarse as
+//! the level inside it, drawn as one instance per level. The vertex shader
+//! derives each vertex from its index, loads its height and reconstructs
+//! the normal from neighbouring heights, so the work of the example is
+//! almost all vertex shading.
+//!
+//! Each level snaps to a grid of twice its own spacing, which puts the
+//! footprint of the finer level exactly on cell boundaries of the coarser
+//! one; the coarser level collapses the cells under that footprint into
+//! degenerate triangles. Every other vertex on the outer border of a level
+//! takes the average height of its neighbours, so it lies on the edge of
+//! the coarser level and no cracks open between levels.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{Mat4, MAT4_IDENTITY};
+use crate::upload_belt;
+
+/// Texels along each side of the heightmap, one per world unit
+pub const HEIGHTMAP_SIZE: u32 = 1024;
+
+/// Cells along each side of a level
+pub const GRID_CELLS: u32 = 128;
+
+/// Levels of detail, each twice as coarse as the one inside it
+pub const LEVELS: u32 = 4;
+
+/// Most noise octaves
+pub const MAX_OCTAVES: u32 = 12;
+
+const WORKGROUP_SIZE: u32 = 8;
+
+const HEIGHTMAP_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub const TERRAIN_HEIGHTMAP_SHADER: &str = r#"// Terrain heightmap generation: fractal Brownian motion, octaves of
+// gradient noise at rising frequency and falling amplitude
+
+struct Noise {
+    octaves: u32,
+    // Of the first octave, per world unit
+    frequency: f32,
+    lacunarity: f32,
+    gain: f32,
+    height: f32,
+    seed: u32,
+    _padding: vec2<u32>,
+}
+
+const HEIGHTMAP_SIZE: u32 = 1024u;
+
+@group(0) @binding(0) var<uniform> noise: Noise;
+@group(0) @binding(1) var heightmap: texture_storage_2d<r32float, write>;
+
+fn hash(x: u32) -> u32 {
+    let state = x * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+fn gradient(cell: vec2<i32>, seed: u32) -> vec2<f32> {
+    let diagonal = 0.70710677;
+    var directions = array<vec2<f32>, 8>(
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(-1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, -1.0),
+        vec2<f32>(diagonal, diagonal),
+        vec2<f32>(-diagonal, diagonal),
+        vec2<f32>(diagonal, -diagonal),
+        vec2<f32>(-diagonal, -diagonal),
+    );
+    let h = hash(bitcast<u32>(cell.x) ^ hash(bitcast<u32>(cell.y) ^ seed));
+    return directions[h & 7u];
+}
+
+fn gradient_noise(p: vec2<f32>, seed: u32) -> f32 {
+    let corner = floor(p);
+    let cell = vec2<i32>(corner);
+    let f = p - corner;
+    let u = f * f * f * (f * (f * 6.0 - 15.0) + 10.0);
+    let a = dot(gradient(cell, seed), f);
+    let b = dot(gradient(cell + vec2<i32>(1, 0), seed), f - vec2<f32>(1.0, 0.0));
+    let c = dot(gradient(cell + vec2<i32>(0, 1), seed), f - vec2<f32>(0.0, 1.0));
+    let d = dot(gradient(cell + vec2<i32>(1, 1), seed), f - vec2<f32>(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+@compute @workgroup_size(8, 8)
+fn generate(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= vec2<u32>(HEIGHTMAP_SIZE)) {
+        return;
+    }
+    // Texel centers sit on whole world units around the origin
+    let world = vec2<f32>(vec2<i32>(id.xy) - i32(HEIGHTMAP_SIZE / 2u));
+    var sum = 0.0;
+    var total = 0.0;
+    var amplitude = 1.0;
+    var frequency = noise.frequency;
+    for (var octave = 0u; octave < noise.octaves; octave++) {
+        sum += gradient_noise(world * frequency, noise.seed + octave) * amplitude;
+        total += amplitude;
+        amplitude *= noise.gain;
+        frequency *= noise.lacunarity;
+    }
+    textureStore(heightmap, id.xy, vec4<f32>(sum / max(total, 1e-6) * noise.height));
+}
+"#;
+
+pub const TERRAIN_RENDER_SHADER: &str = r#"// Procedural Terrain Example
+// No vertex buffers: the vertex index picks a cell and a corner of one of
+// its two triangles, the instance index picks the level of detail
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    // Camera position the levels center on
+    center: vec2<f32>,
+    height_scale: f32,
+    wireframe: u32,
+    fog_color: vec3<f32>,
+    show_levels: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    // Position in cells of the level, for the wireframe
+    @location(2) grid: vec2<f32>,
+    @location(3) @interpolate(flat) level: u32,
+}
+
+const GRID_CELLS: u32 = 128u;
+const HEIGHTMAP_SIZE: i32 = 1024;
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var heightmap: texture_2d<f32>;
+
+fn height_at(world: vec2<f32>) -> f32 {
+    let texel = vec2<i32>(round(world)) + HEIGHTMAP_SIZE / 2;
+    return textureLoad(heightmap, clamp(texel, vec2<i32>(0), vec2<i32>(HEIGHTMAP_SIZE - 1)), 0).x;
+}
+
+fn spacing(level: u32) -> f32 {
+    return f32(1u << level);
+}
+
+// World position of the center of a level, on a grid of twice its spacing
+fn level_origin(level: u32) -> vec2<f32> {
+    let snap = 2.0 * spacing(level);
+    return round(uniforms.center / snap) * snap;
+}
+
+// Whether the finer level already covers the cell with corner `cell_min`
+fn covered_by_finer(level: u32, cell_min: vec2<f32>) -> bool {
+    if level == 0u {
+        return false;
+    }
+    let fine_half = f32(GRID_CELLS / 2u) * spacing(level - 1u);
+    let fine_min = level_origin(level - 1u) - fine_half;
+    let fine_max = level_origin(level - 1u) + fine_half;
+    let cell_max = cell_min + spacing(level);
+    let epsilon = spacing(level) * 0.25;
+    return all(cell_min > fine_min - epsilon) && all(cell_max < fine_max + epsilon);
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex: u32,
+    @builtin(instance_index) level: u32,
+) -> VertexOutput {
+    var corners = array<vec2<i32>, 6>(
+        vec2<i32>(0, 0),
+        vec2<i32>(1, 0),
+        vec2<i32>(1, 1),
+        vec2<i32>(0, 0),
+        vec2<i32>(1, 1),
+        vec2<i32>(0, 1),
+    );
+    let index = vertex / 6u;
+    let cell = vec2<i32>(i32(index % GRID_CELLS), i32(index / GRID_CELLS));
+    let grid = cell + corners[vertex % 6u];
+    let step = spacing(level);
+    let origin = level_origin(level);
+    let half = i32(GRID_CELLS / 2u);
+
+    var out: VertexOutput;
+    out.level = level;
+    if covered_by_finer(level, origin + vec2<f32>(cell - half) * step) {
+        // Degenerate and outside the depth range
+        out.position = vec4<f32>(0.0, 0.0, -1.0, 1.0);
+        return out;
+    }
+
+    let world = origin + vec2<f32>(grid - half) * step;
+    var height = height_at(world);
+    // Odd vertices on the outer border follow the coarser level's edge
+    let last = i32(GRID_CELLS);
+    if (grid.x == 0 || grid.x == last) && (grid.y & 1) == 1 {
+        height = 0.5 * (height_at(world - vec2<f32>(0.0, step)) + height_at(world + vec2<f32>(0.0, step)));
+    } else if (grid.y == 0 || grid.y == last) && (grid.x & 1) == 1 {
+        height = 0.5 * (height_at(world - vec2<f32>(step, 0.0)) + height_at(world + vec2<f32>(step, 0.0)));
+    }
+
+    // Central differences over the level's spacing
+    let left = height_at(world - vec2<f32>(step, 0.0));
+    let right = height_at(world + vec2<f32>(step, 0.0));
+    let back = height_at(world - vec2<f32>(0.0, step));
+    let front = height_at(world + vec2<f32>(0.0, step));
+
+    out.world = vec3<f32>(world.x, height, world.y);
+    out.position = uniforms.view_proj * vec4<f32>(out.world, 1.0);
+    out.normal = normalize(vec3<f32>(left - right, 2.0 * step, back - front));
+    out.grid = vec2<f32>(grid);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Distance in pixels to the nearest edge of the cell's two triangles;
+    // derivatives first, while control flow is uniform
+    let cell = fract(in.grid);
+    let width = fwidth(in.grid);
+    let diagonal = abs(cell.x - cell.y) / max(fwidth(in.grid.x - in.grid.y), 1e-6);
+    let edge = min(min(min(cell.x, 1.0 - cell.x) / width.x, min(cell.y, 1.0 - cell.y) / width.y), diagonal);
+
+    let normal = normalize(in.normal);
+    let elevation = in.world.y / max(uniforms.height_scale, 1e-3);
+    let grass = vec3<f32>(0.24, 0.42, 0.16);
+    let soil = vec3<f32>(0.45, 0.38, 0.25);
+    let rock = vec3<f32>(0.42, 0.40, 0.38);
+    let snow = vec3<f32>(0.92, 0.93, 0.95);
+    var color = mix(soil, grass, smoothstep(-0.35, -0.2, elevation));
+    color = mix(color, rock, smoothstep(0.25, 0.45, 1.0 - normal.y));
+    color = mix(color, snow, smoothstep(0.28, 0.36, elevation) * smoothstep(0.5, 0.8, normal.y));
+
+    if uniforms.show_levels == 1u {
+        var tints = array<vec3<f32>, 4>(
+            vec3<f32>(1.0, 0.45, 0.45),
+            vec3<f32>(0.45, 1.0, 0.45),
+            vec3<f32>(0.45, 0.6, 1.0),
+            vec3<f32>(1.0, 0.9, 0.4),
+        );
+        color *= tints[in.level % 4u] * 1.3;
+    }
+
+    let light = normalize(vec3<f32>(0.4, 0.8, 0.3));
+    color *= 0.2 + 0.8 * max(dot(normal, light), 0.0);
+
+    if uniforms.wireframe == 1u {
+        color = mix(vec3<f32>(0.02), color, smoothstep(0.0, 1.0, edge));
+    }
+
+    // Distance fog hides where the coarsest level ends
+    let distance = length(in.world.xz - uniforms.center);
+    let fog = smoothstep(250.0, 500.0, distance);
+    return vec4<f32>(mix(color, uniforms.fog_color, fog), 1.0);
+}
+"#;
+
+/// Noise parameters of the heightmap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainNoise {
+    /// Octaves summed, up to [`MAX_OCTAVES`]
+    pub octaves: u32,
+    /// Frequency of the first octave, per world unit
+    pub frequency: f32,
+    /// Frequency factor from one octave to the next
+    pub lacunarity: f32,
+    /// Amplitude factor from one octave to the next
+    pub gain: f32,
+    /// Height of the noise's extremes, in world units
+    pub height: f32,
+    pub seed: u32,
+}
+
+impl Default for TerrainNoise {
+    fn default() -> Self {
+        Self {
+            octaves: 6,
+            frequency: 1.0 / 200.0,
+            lacunarity: 2.0,
+            gain: 0.5,
+            height: 80.0,
+            seed: 1,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct NoiseUniform {
+    octaves: u32,
+    frequency: f32,
+    lacunarity: f32,
+    gain: f32,
+    height: f32,
+    seed: u32,
+    _padding: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: Mat4,
+    center: [f32; 2],
+    height_scale: f32,
+    wireframe: u32,
+    fog_color: [f32; 3],
+    show_levels: u32,
+}
+
+fn hash(x: u32) -> u32 {
+    let state = x.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    (word >> 22) ^ word
+}
+
+fn gradient(cell: [i32; 2], seed: u32) -> [f32; 2] {
+    const DIAGONAL: f32 = std::f32::consts::FRAC_1_SQRT_2;
+    const DIRECTIONS: [[f32; 2]; 8] = [
+        [1.0, 0.0],
+        [-1.0, 0.0],
+        [0.0, 1.0],
+        [0.0, -1.0],
+        [DIAGONAL, DIAGONAL],
+        [-DIAGONAL, DIAGONAL],
+        [DIAGONAL, -DIAGONAL],
+        [-DIAGONAL, -DIAGONAL],
+    ];
+    let h = hash(cell[0] as u32 ^ hash(cell[1] as u32 ^ seed));
+    DIRECTIONS[(h & 7) as usize]
+}
+
+fn gradient_noise(p: [f32; 2], seed: u32) -> f32 {
+    let corner = [p[0].floor(), p[1].floor()];
+    let cell = [corner[0] as i32, corner[1] as i32];
+    let f = [p[0] - corner[0], p[1] - corner[1]];
+    let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let u = [fade(f[0]), fade(f[1])];
+    let dot = |offset: [i32; 2]| {
+        let g = gradient([cell[0] + offset[0], cell[1] + offset[1]], seed);
+        g[0] * (f[0] - offset[0] as f32) + g[1] * (f[1] - offset[1] as f32)
+    };
+    let mix = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    mix(
+        mix(dot([0, 0]), dot([1, 0]), u[0]),
+        mix(dot([0, 1]), dot([1, 1]), u[0]),
+        u[1],
+    )
+}
+
+/// Height of the terrain at a world position, as the heightmap shader
+/// computes it for the texel there
+pub fn terrain_height(noise: &TerrainNoise, x: f32, z: f32) -> f32 {
+    let mut sum = 0.0;
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = noise.frequency;
+    for octave in 0..noise.octaves.min(MAX_OCTAVES) {
+        sum += gradient_noise([x * frequency, z * frequency], noise.seed + octave) * amplitude;
+        total += amplitude;
+        amplitude *= noise.gain;
+        frequency *= noise.lacunarity;
+    }
+    sum / f32::max(total, 1e-6) * noise.height
+}
+
+/// Vertices shaded per frame, including the collapsed ones
+pub fn vertices_per_frame() -> u32 {
+    GRID_CELLS * GRID_CELLS * 6 * LEVELS
+}
+
+/// Triangles that are not collapsed, per frame
+pub fn triangles_per_frame() -> u32 {
+    let full = GRID_CELLS * GRID_CELLS * 2;
+    let ring = full - full / 4;
+    full + ring * (LEVELS - 1)
+}
+
+/// Renderer of the terrain example
+pub struct Terrain {
+    generate_pipeline: wgpu::ComputePipeline,
+    render_pipeline: wgpu::RenderPipeline,
+    generate_bind_group: wgpu::BindGroup,
+    render_bind_group: wgpu::BindGroup,
+    noise_buffer: wgpu::Buffer,
+    uniform_buffer: wgpu::Buffer,
+    heightmap: wgpu::Texture,
+    depth_view: wgpu::TextureView,
+    size: (u32, u32),
+    view_proj: Mat4,
+    center: [f32; 2],
+    /// Noise the heightmap was generated with, `None` before the first
+    generated: Option<TerrainNoise>,
+    pub noise: TerrainNoise,
+    /// Draw the edges of the triangles over the shading
+    pub wireframe: bool,
+    /// Tint every level of detail differently
+    pub show_levels: bool,
+}
+
+impl Terrain {
+    /// Create the renderer drawing into `color_format` targets of `width`x`height`
+    pub fn new(
+        device: &wgpu::Device,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let generate_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Terrain Heightmap Shader"),
+            source: wgpu::ShaderSource::Wgsl(TERRAIN_HEIGHTMAP_SHADER.into()),
+        });
+        let render_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Terrain Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(TERRAIN_RENDER_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let heightmap = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Terrain Heightmap"),
+            size: wgpu::Extent3d {
+                width: HEIGHTMAP_SIZE,
+                height: HEIGHTMAP_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HEIGHTMAP_FORMAT,
+            usage: wgpu::TextureUsages::STORAGE_BINDING
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let heightmap_view = heightmap.create_view(&wgpu::TextureViewDescriptor::default());
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let noise_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Terrain Noise"),
+            size: std::mem::size_of::<NoiseUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Terrain Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let uniform_entry = |visibility| wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let generate_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Terrain Heightmap Bind Group Layout"),
+            entries: &[
+                uniform_entry(wgpu::ShaderStages::COMPUTE),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: HEIGHTMAP_FORMAT,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let render_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Terrain Render Bind Group Layout"),
+            entries: &[
+                uniform_entry(wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT),
+                // 32-bit floats are not filterable, the vertex shader loads texels
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let generate_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Terrain Heightmap Bind Group"),
+            layout: &generate_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: noise_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&heightmap_view),
+                },
+            ],
+        });
+        let render_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Terrain Render Bind Group"),
+            layout: &render_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&heightmap_view),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let generate_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Terrain Heightmap Pipeline Layout"),
+                bind_group_layouts: &[Some(&generate_layout)],
+                immediate_size: 0,
+            });
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Terrain Render Pipeline Layout"),
+                bind_group_layouts: &[Some(&render_layout)],
+                immediate_size: 0,
+            });
+
+        tracker.record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+        let generate_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Terrain Heightmap Pipeline"),
+            layout: Some(&generate_pipeline_layout),
+            module: &generate_shader,
+            entry_point: Some("generate"),
+            compilation_options: Default::default(),
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Terrain Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &render_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            // The cells are wound differently on either diagonal half, and
+            // the camera may look at the terrain from below
+            primitive: wgpu::PrimitiveState {
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Self {
+            generate_pipeline,
+            render_pipeline,
+            generate_bind_group,
+            render_bind_group,
+            noise_buffer,
+            uniform_buffer,
+            heightmap,
+            depth_view: create_depth_view(device, width, height),
+            size: (width, height),
+            view_proj: MAT4_IDENTITY,
+            center: [0.0; 2],
+            generated: None,
+            noise: TerrainNoise::default(),
+            wireframe: false,
+            show_levels: false,
+        }
+    }
+
+    /// The `r32float` heightmap, regenerated on the next render after the
+    /// noise changes
+    pub fn heightmap(&self) -> &wgpu::Texture {
+        &self.heightmap
+    }
+
+    /// Center the levels of detail on the camera at `eye`
+    pub fn update(&mut self, view_proj: Mat4, eye: [f32; 3]) {
+        self.view_proj = view_proj;
+        self.center = [eye[0], eye[2]];
+    }
+
+    /// Regenerate the heightmap if the noise changed, then draw the terrain
+    /// into `target`, a view of a texture of the given size, fading into
+    /// `clear_color` in the distance
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        if self.size != (width, height) {
+            self.depth_view = create_depth_view(device, width, height);
+            self.size = (width, height);
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let regenerate = self.generated != Some(self.noise);
+        if regenerate {
+            let noise = NoiseUniform {
+                octaves: self.noise.octaves.min(MAX_OCTAVES),
+                frequency: self.noise.frequency,
+                lacunarity: self.noise.lacunarity,
+                gain: self.noise.gain,
+                height: self.noise.height,
+                seed: self.noise.seed,
+                _padding: [0; 2],
+            };
+            upload_belt::write_buffer(queue, &self.noise_buffer, 0, bytemuck::bytes_of(&noise));
+        }
+        let uniforms = Uniforms {
+            view_proj: self.view_proj,
+            center: self.center,
+            height_scale: self.noise.height,
+            wireframe: u32::from(self.wireframe),
+            fog_color: [clear_color.r, clear_color.g, clear_color.b].map(|c| c as f32),
+            show_levels: u32::from(self.show_levels),
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Terrain Encoder"),
+        });
+        if regenerate {
+            tracker.record(ApiCategory::ComputePass, "begin_compute_pass");
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Terrain Heightmap Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.generate_pipeline);
+            compute_pass.set_bind_group(0, &self.generate_bind_group, &[]);
+            tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+            let workgroups = HEIGHTMAP_SIZE.div_ceil(WORKGROUP_SIZE);
+            compute_pass.dispatch_workgroups(workgroups, workgroups, 1);
+            self.generated = Some(self.noise);
+        }
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Terrain Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+            // Six vertices per cell, one instance per level of detail
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..GRID_CELLS * GRID_CELLS * 6, 0..LEVELS);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+}
+
+fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Terrain Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_shaders_are_valid() {
+        validate(TERRAIN_HEIGHTMAP_SHADER);
+        validate(TERRAIN_RENDER_SHADER);
+        let size = format!("const HEIGHTMAP_SIZE: u32 = {}u;", HEIGHTMAP_SIZE);
+        assert!(TERRAIN_HEIGHTMAP_SHADER.contains(&size));
+        let size = format!("const HEIGHTMAP_SIZE: i32 = {};", HEIGHTMAP_SIZE);
+        assert!(TERRAIN_RENDER_SHADER.contains(&size));
+        let cells = format!("const GRID_CELLS: u32 = {}u;", GRID_CELLS);
+        assert!(TERRAIN_RENDER_SHADER.contains(&cells));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<NoiseUniform>(), 32);
+        assert_eq!(std::mem::size_of::<Uniforms>(), 96);
+    }
+
+    #[test]
+    fn test_coarsest_level_covers_the_heightmap() {
+        let extent = GRID_CELLS as f32 * (1 << (LEVELS - 1)) as f32;
+        assert_eq!(extent, HEIGHTMAP_SIZE as f32);
+    }
+
+    #[test]
+    fn test_height_is_bounded_and_deterministic() {
+        let noise = TerrainNoise::default();
+        for i in 0..200 {
+            let (x, z) = (i as f32 * 7.3 - 500.0, i as f32 * -3.1 + 200.0);
+            let height = terrain_height(&noise, x, z);
+            assert!(height.abs() <= noise.height, "{} at {},{}", height, x, z);
+            assert_eq!(height, terrain_height(&noise, x, z));
+        }
+        let other = TerrainNoise { seed: 2, ..noise };
+        assert_ne!(
+            terrain_height(&noise, 10.0, 10.0),
+            terrain_height(&other, 10.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn test_gradient_noise_is_zero_on_lattice_points() {
+        for (x, y) in [(0.0, 0.0), (3.0, -2.0), (-7.0, 5.0)] {
+            assert_eq!(gradient_noise([x, y], 1), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_triangle_counts() {
+        assert_eq!(vertices_per_frame(), GRID_CELLS * GRID_CELLS * 6 * LEVELS);
+        // The finer level covers a quarter of every coarser one
+        assert_eq!(
+            triangles_per_frame(),
+            GRID_CELLS * GRID_CELLS * 2 * (4 + (LEVELS - 1) * 3) / 4
+        );
+    }
+}
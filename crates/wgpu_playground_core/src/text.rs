@@ -0,0 +1,962 @@
+//! Text drawn in user render passes from a glyph atlas
+//!
+//! [`TextRenderer`] lays out [`TextSection`]s with `ab_glyph`, rasterizes
+//! every glyph the first time it is needed into an `r8unorm` atlas texture
+//! and draws one textured quad per glyph, instanced from a buffer rebuilt by
+//! [`TextRenderer::prepare`]. [`TextRenderer::render`] only records draw
+//! commands, so the text can go into any render pass with a matching color
+//! target, for example to label objects in a scene, independently of egui.
+//!
+//! Sections are anchored either to a pixel of the target or to a point in
+//! the scene, which is projected through the view-projection matrix given
+//! to `prepare`; labels of scene points stay the same size on screen and,
+//! with a depth format, hide behind nearer geometry.
+
+use std::collections::HashMap;
+
+use ab_glyph::{Font, FontArc, Glyph, GlyphId, PxScale, ScaleFont};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{Mat4, MAT4_IDENTITY};
+use crate::upload_belt;
+
+/// DejaVu Sans Mono, the font used unless [`TextRenderer::set_font`] replaces it
+pub const DEFAULT_FONT: &[u8] = include_bytes!("../../../assets/fonts/DejaVuSansMono.ttf");
+
+/// Texels along each side of the glyph atlas
+pub const ATLAS_SIZE: u32 = 1024;
+
+/// Empty texels around every glyph in the atlas, so that linear filtering
+/// never reads a neighbour
+const GLYPH_PADDING: u32 = 1;
+
+const ATLAS_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+
+pub const TEXT_SHADER: &str = r#"// Text Rendering
+// One instanced quad per glyph: a rectangle in pixels of the target and the
+// glyph's coverage in the atlas
+
+struct Uniforms {
+    viewport: vec2<f32>,
+    _padding: vec2<f32>,
+}
+
+struct GlyphInstance {
+    // Top-left corner and size, in pixels
+    @location(0) rect: vec4<f32>,
+    // Top-left corner and size in the atlas, in texture coordinates
+    @location(1) uv_rect: vec4<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) depth: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var atlas: texture_2d<f32>;
+@group(0) @binding(2) var atlas_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex: u32, glyph: GlyphInstance) -> VertexOutput {
+    // Triangle strip over the corners
+    let corner = vec2<f32>(f32(vertex & 1u), f32(vertex >> 1u));
+    let pixel = glyph.rect.xy + corner * glyph.rect.zw;
+    let ndc = vec2<f32>(pixel.x / uniforms.viewport.x * 2.0 - 1.0, 1.0 - pixel.y / uniforms.viewport.y * 2.0);
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc, glyph.depth, 1.0);
+    out.uv = glyph.uv_rect.xy + corner * glyph.uv_rect.zw;
+    out.color = glyph.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(atlas, atlas_sampler, in.uv).r;
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
+"#;
+
+/// Errors from laying out and rasterizing text
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextError {
+    /// The font data could not be parsed
+    InvalidFont,
+    /// The glyphs of a single frame don't fit in the atlas
+    AtlasFull,
+}
+
+impl std::fmt::Display for TextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextError::InvalidFont => write!(f, "Invalid font data"),
+            TextError::AtlasFull => write!(
+                f,
+                "The glyphs don't fit in the {0}x{0} atlas; use fewer sizes or characters",
+                ATLAS_SIZE
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TextError {}
+
+/// What a [`TextSection`] is positioned relative to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextAnchor {
+    /// Top-left corner of the text, in pixels from the top-left of the target
+    Screen([f32; 2]),
+    /// Center of the text on a point in the scene
+    World([f32; 3]),
+}
+
+/// A block of text, one line per `\n`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSection {
+    pub text: String,
+    pub anchor: TextAnchor,
+    /// Pixels added to the anchored position
+    pub offset: [f32; 2],
+    /// Font size in pixels
+    pub size: f32,
+    /// Linear RGBA
+    pub color: [f32; 4],
+}
+
+impl TextSection {
+    /// White 16-pixel text at `anchor`
+    pub fn new(text: impl Into<String>, anchor: TextAnchor) -> Self {
+        Self {
+            text: text.into(),
+            anchor,
+            offset: [0.0; 2],
+            size: 16.0,
+            color: [1.0; 4],
+        }
+    }
+
+    pub fn with_offset(mut self, offset: [f32; 2]) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// A glyph placed by [`layout`], relative to the top-left of its section
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    pub id: GlyphId,
+    /// Whole pixels, so the atlas texels map one to one onto the target
+    pub origin: [f32; 2],
+}
+
+/// The glyphs of `text` at `size` pixels, and the size of the block they fill
+pub fn layout(font: &FontArc, text: &str, size: f32) -> (Vec<PositionedGlyph>, [f32; 2]) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let line_height = scaled.height() + scaled.line_gap();
+    let mut glyphs = Vec::with_capacity(text.len());
+    let mut width = 0.0_f32;
+    let mut lines = 1;
+    let mut pen_x = 0.0;
+    let mut previous: Option<GlyphId> = None;
+    for c in text.chars() {
+        if c == '\n' {
+            lines += 1;
+            pen_x = 0.0;
+            previous = None;
+            continue;
+        }
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            pen_x += scaled.kern(previous, id);
+        }
+        let baseline = scaled.ascent() + line_height * (lines - 1) as f32;
+        glyphs.push(PositionedGlyph {
+            id,
+            origin: [pen_x.round(), baseline.round()],
+        });
+        pen_x += scaled.h_advance(id);
+        width = width.max(pen_x);
+        previous = Some(id);
+    }
+    let height = scaled.height() + line_height * (lines - 1) as f32;
+    (glyphs, [width.ceil(), height.ceil()])
+}
+
+/// Where a section lands on a `viewport` sized target: the top-left corner of
+/// its block in pixels and its depth, or `None` behind the camera
+pub fn anchor_position(
+    anchor: TextAnchor,
+    block: [f32; 2],
+    view_proj: &Mat4,
+    viewport: [f32; 2],
+) -> Option<([f32; 2], f32)> {
+    match anchor {
+        TextAnchor::Screen(position) => Some((position, 0.0)),
+        TextAnchor::World(point) => {
+            let [x, y, z, w] = std::array::from_fn(|row| {
+                view_proj[0][row] * point[0]
+                    + view_proj[1][row] * point[1]
+                    + view_proj[2][row] * point[2]
+                    + view_proj[3][row]
+            });
+            let depth = z / w;
+            if w <= 0.0 || !(0.0..=1.0).contains(&depth) {
+                return None;
+            }
+            let pixel = [
+                (x / w * 0.5 + 0.5) * viewport[0],
+                (0.5 - y / w * 0.5) * viewport[1],
+            ];
+            Some((
+                [pixel[0] - block[0] / 2.0, pixel[1] - block[1] / 2.0],
+                depth,
+            ))
+        }
+    }
+}
+
+/// Rows of rectangles filled left to right, a new row under the tallest
+/// rectangle of the last once a row is full
+#[derive(Debug, Clone, Default)]
+struct ShelfPacker {
+    x: u32,
+    y: u32,
+    row_height: u32,
+}
+
+impl ShelfPacker {
+    /// Top-left corner of a free `width`x`height` rectangle
+    fn allocate(&mut self, width: u32, height: u32) -> Option<[u32; 2]> {
+        let (width, height) = (width + GLYPH_PADDING, height + GLYPH_PADDING);
+        if width > ATLAS_SIZE {
+            return None;
+        }
+        if self.x + width > ATLAS_SIZE {
+            self.y += self.row_height;
+            self.x = 0;
+            self.row_height = 0;
+        }
+        if self.y + height > ATLAS_SIZE {
+            return None;
+        }
+        let corner = [self.x, self.y];
+        self.x += width;
+        self.row_height = self.row_height.max(height);
+        Some(corner)
+    }
+
+    /// Fraction of the atlas rows in use
+    fn usage(&self) -> f32 {
+        (self.y + self.row_height) as f32 / ATLAS_SIZE as f32
+    }
+}
+
+/// Where a rasterized glyph is in the atlas
+#[derive(Debug, Clone, Copy)]
+struct AtlasGlyph {
+    corner: [u32; 2],
+    size: [u32; 2],
+    /// From the glyph's origin on the baseline to its top-left texel
+    bearing: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct GlyphInstance {
+    rect: [f32; 4],
+    uv_rect: [f32; 4],
+    color: [f32; 4],
+    depth: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    viewport: [f32; 2],
+    _padding: [f32; 2],
+}
+
+/// Draws [`TextSection`]s into render passes of one color format
+pub struct TextRenderer {
+    font: FontArc,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    atlas: wgpu::Texture,
+    packer: ShelfPacker,
+    /// Rasterized glyphs by id and pixel size; `None` for glyphs without
+    /// an outline, such as spaces
+    glyphs: HashMap<(GlyphId, u32), Option<AtlasGlyph>>,
+    instance_buffer: wgpu::Buffer,
+    instance_count: u32,
+}
+
+impl TextRenderer {
+    /// Create a renderer for passes with a `color_format` target, a
+    /// `depth_format` attachment if any, and `sample_count` samples
+    ///
+    /// With a depth attachment, text anchored in the scene is tested
+    /// against the depth of the scene without writing to it.
+    pub fn new(
+        device: &wgpu::Device,
+        color_format: wgpu::TextureFormat,
+        depth_format: Option<wgpu::TextureFormat>,
+        sample_count: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Text Shader"),
+            source: wgpu::ShaderSource::Wgsl(TEXT_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let atlas = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Glyph Atlas"),
+            size: wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: ATLAS_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let atlas_view = atlas.create_view(&wgpu::TextureViewDescriptor::default());
+
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Glyph Atlas Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Text Uniform Buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Text Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Text Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&atlas_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Text Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Text Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphInstance>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x4,
+                        1 => Float32x4,
+                        2 => Float32x4,
+                        3 => Float32
+                    ],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: depth_format.map(|format| wgpu::DepthStencilState {
+                format,
+                depth_write_enabled: Some(false),
+                depth_compare: Some(wgpu::CompareFunction::LessEqual),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        Self {
+            font: FontArc::try_from_slice(DEFAULT_FONT).expect("the bundled font parses"),
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            atlas,
+            packer: ShelfPacker::default(),
+            glyphs: HashMap::new(),
+            instance_buffer: create_instance_buffer(device, 256),
+            instance_count: 0,
+        }
+    }
+
+    /// Replace the font with TrueType or OpenType `data`, emptying the atlas
+    pub fn set_font(&mut self, data: Vec<u8>) -> Result<(), TextError> {
+        self.font = FontArc::try_from_vec(data).map_err(|_| TextError::InvalidFont)?;
+        self.clear_atlas();
+        Ok(())
+    }
+
+    pub fn font(&self) -> &FontArc {
+        &self.font
+    }
+
+    /// Size in pixels of the block `text` fills at `size` pixels
+    pub fn measure(&self, text: &str, size: f32) -> [f32; 2] {
+        layout(&self.font, text, font_size(size) as f32).1
+    }
+
+    /// The `r8unorm` atlas of rasterized glyph coverage
+    pub fn atlas(&self) -> &wgpu::Texture {
+        &self.atlas
+    }
+
+    /// Glyphs rasterized into the atlas so far
+    pub fn atlas_glyphs(&self) -> usize {
+        self.glyphs.values().flatten().count()
+    }
+
+    /// Fraction of the atlas in use
+    pub fn atlas_usage(&self) -> f32 {
+        self.packer.usage()
+    }
+
+    /// Glyph quads drawn by [`TextRenderer::render`]
+    pub fn glyph_count(&self) -> u32 {
+        self.instance_count
+    }
+
+    fn clear_atlas(&mut self) {
+        self.glyphs.clear();
+        self.packer = ShelfPacker::default();
+    }
+
+    /// Lay out `sections` for a `viewport` sized target, rasterizing new
+    /// glyphs into the atlas; `view_proj` places the sections anchored in
+    /// the scene
+    ///
+    /// A full atlas is emptied once and filled with only this frame's glyphs.
+    /// Buffers are updated through the upload belt, so on the playground's
+    /// device submit the pass with [`upload_belt::submit`].
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        sections: &[TextSection],
+        (width, height): (u32, u32),
+        view_proj: &Mat4,
+    ) -> Result<(), TextError> {
+        let instances = match self.build_instances(queue, sections, (width, height), view_proj) {
+            Err(TextError::AtlasFull) => {
+                self.clear_atlas();
+                self.build_instances(queue, sections, (width, height), view_proj)
+            }
+            result => result,
+        };
+        let instances = match instances {
+            Ok(instances) => instances,
+            Err(error) => {
+                self.instance_count = 0;
+                return Err(error);
+            }
+        };
+
+        let needed = (instances.len() * std::mem::size_of::<GlyphInstance>()) as u64;
+        if needed > self.instance_buffer.size() {
+            self.instance_buffer =
+                create_instance_buffer(device, instances.len().next_power_of_two());
+        }
+        if !instances.is_empty() {
+            upload_belt::write_buffer(
+                queue,
+                &self.instance_buffer,
+                0,
+                bytemuck::cast_slice(&instances),
+            );
+        }
+        let uniforms = Uniforms {
+            viewport: [width.max(1) as f32, height.max(1) as f32],
+            _padding: [0.0; 2],
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+        self.instance_count = instances.len() as u32;
+        Ok(())
+    }
+
+    fn build_instances(
+        &mut self,
+        queue: &wgpu::Queue,
+        sections: &[TextSection],
+        (width, height): (u32, u32),
+        view_proj: &Mat4,
+    ) -> Result<Vec<GlyphInstance>, TextError> {
+        let viewport = [width as f32, height as f32];
+        let mut instances = Vec::new();
+        for section in sections {
+            let size = font_size(section.size);
+            let (glyphs, block) = layout(&self.font, &section.text, size as f32);
+            let Some((corner, depth)) = anchor_position(section.anchor, block, view_proj, viewport)
+            else {
+                continue;
+            };
+            let corner = [
+                (corner[0] + section.offset[0]).round(),
+                (corner[1] + section.offset[1]).round(),
+            ];
+            for glyph in glyphs {
+                let Some(atlas_glyph) = self.rasterize(queue, glyph.id, size)? else {
+                    continue;
+                };
+                let [w, h] = atlas_glyph.size.map(|s| s as f32);
+                instances.push(GlyphInstance {
+                    rect: [
+                        corner[0] + glyph.origin[0] + atlas_glyph.bearing[0],
+                        corner[1] + glyph.origin[1] + atlas_glyph.bearing[1],
+                        w,
+                        h,
+                    ],
+                    uv_rect: [
+                        atlas_glyph.corner[0] as f32 / ATLAS_SIZE as f32,
+                        atlas_glyph.corner[1] as f32 / ATLAS_SIZE as f32,
+                        w / ATLAS_SIZE as f32,
+                        h / ATLAS_SIZE as f32,
+                    ],
+                    color: section.color,
+                    depth,
+                });
+            }
+        }
+        Ok(instances)
+    }
+
+    /// The atlas entry of a glyph at `size` pixels, rasterizing it on first use
+    fn rasterize(
+        &mut self,
+        queue: &wgpu::Queue,
+        id: GlyphId,
+        size: u32,
+    ) -> Result<Option<AtlasGlyph>, TextError> {
+        if let Some(entry) = self.glyphs.get(&(id, size)) {
+            return Ok(*entry);
+        }
+        let glyph: Glyph = id.with_scale(size as f32);
+        let Some(outline) = self.font.outline_glyph(glyph) else {
+            self.glyphs.insert((id, size), None);
+            return Ok(None);
+        };
+        let bounds = outline.px_bounds();
+        let glyph_size = [bounds.width() as u32, bounds.height() as u32];
+        if glyph_size[0] == 0 || glyph_size[1] == 0 {
+            self.glyphs.insert((id, size), None);
+            return Ok(None);
+        }
+        let corner = self
+            .packer
+            .allocate(glyph_size[0], glyph_size[1])
+            .ok_or(TextError::AtlasFull)?;
+
+        let mut coverage = vec![0u8; (glyph_size[0] * glyph_size[1]) as usize];
+        outline.draw(|x, y, c| {
+            if x < glyph_size[0] && y < glyph_size[1] {
+                coverage[(y * glyph_size[0] + x) as usize] = (c.clamp(0.0, 1.0) * 255.0) as u8;
+            }
+        });
+        ApiCoverageTracker::global().record(ApiCategory::Queue, "write_texture");
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.atlas,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: corner[0],
+                    y: corner[1],
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &coverage,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(glyph_size[0]),
+                rows_per_image: Some(glyph_size[1]),
+            },
+            wgpu::Extent3d {
+                width: glyph_size[0],
+                height: glyph_size[1],
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let entry = AtlasGlyph {
+            corner,
+            size: glyph_size,
+            bearing: [bounds.min.x, bounds.min.y],
+        };
+        self.glyphs.insert((id, size), Some(entry));
+        Ok(Some(entry))
+    }
+
+    /// Record the draw of the last prepared text into `render_pass`
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        if self.instance_count == 0 {
+            return;
+        }
+        let tracker = ApiCoverageTracker::global();
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        tracker.record(ApiCategory::RenderPass, "set_vertex_buffer");
+        render_pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+        // Four corners as a strip, one instance per glyph
+        tracker.record(ApiCategory::RenderPass, "draw");
+        render_pass.draw(0..4, 0..self.instance_count);
+    }
+}
+
+/// Glyphs are cached per whole pixel size
+fn font_size(size: f32) -> u32 {
+    size.round().max(1.0) as u32
+}
+
+fn create_instance_buffer(device: &wgpu::Device, glyphs: usize) -> wgpu::Buffer {
+    ApiCoverageTracker::global().record(ApiCategory::Buffer, "create_buffer");
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Glyph Instance Buffer"),
+        size: (glyphs.max(1) * std::mem::size_of::<GlyphInstance>()) as u64,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Scene of the text rendering example: the corners and axes of a cube
+/// labelled in the scene, and a heads-up display in screen space
+pub struct TextScene {
+    renderer: TextRenderer,
+    view_proj: Mat4,
+    /// Label drawn at the center of the scene
+    pub label: String,
+    /// Font size of the labels, in pixels
+    pub label_size: f32,
+    pub show_corners: bool,
+    error: Option<TextError>,
+}
+
+impl TextScene {
+    pub fn new(device: &wgpu::Device, color_format: wgpu::TextureFormat) -> Self {
+        Self {
+            renderer: TextRenderer::new(device, color_format, None, 1),
+            view_proj: MAT4_IDENTITY,
+            label: "Hello, wgpu!".to_string(),
+            label_size: 32.0,
+            show_corners: true,
+            error: None,
+        }
+    }
+
+    pub fn renderer(&self) -> &TextRenderer {
+        &self.renderer
+    }
+
+    /// The error of the last frame's layout, if any
+    pub fn error(&self) -> Option<&TextError> {
+        self.error.as_ref()
+    }
+
+    pub fn update(&mut self, view_proj: Mat4) {
+        self.view_proj = view_proj;
+    }
+
+    /// The sections of one frame
+    pub fn sections(&self) -> Vec<TextSection> {
+        let mut sections = vec![
+            TextSection::new(self.label.clone(), TextAnchor::World([0.0; 3]))
+                .with_size(self.label_size)
+                .with_color([1.0, 0.85, 0.3, 1.0]),
+        ];
+
+        let axes = [
+            ("X", [1.5, 0.0, 0.0], [1.0, 0.3, 0.3, 1.0]),
+            ("Y", [0.0, 1.5, 0.0], [0.3, 1.0, 0.3, 1.0]),
+            ("Z", [0.0, 0.0, 1.5], [0.4, 0.5, 1.0, 1.0]),
+        ];
+        for (name, point, color) in axes {
+            sections.push(
+                TextSection::new(name, TextAnchor::World(point))
+                    .with_size(self.label_size * 0.75)
+                    .with_color(color),
+            );
+        }
+
+        if self.show_corners {
+            let size = (self.label_size * 0.5).max(8.0);
+            for corner in 0..8 {
+                let point =
+                    [0, 1, 2].map(|axis| if (corner >> axis) & 1 == 1 { 1.0 } else { -1.0 });
+                let sign = |v: f32| if v > 0.0 { '+' } else { '-' };
+                sections.push(
+                    TextSection::new("●", TextAnchor::World(point))
+                        .with_size(size)
+                        .with_color([0.9, 0.9, 0.9, 1.0]),
+                );
+                sections.push(
+                    TextSection::new(
+                        format!(
+                            "({}1,{}1,{}1)",
+                            sign(point[0]),
+                            sign(point[1]),
+                            sign(point[2])
+                        ),
+                        TextAnchor::World(point),
+                    )
+                    .with_offset([0.0, -size])
+                    .with_size(size)
+                    .with_color([0.75, 0.75, 0.75, 1.0]),
+                );
+            }
+        }
+
+        sections.push(
+            TextSection::new(
+                format!(
+                    "Glyph atlas: {} glyphs, {:.1}% used\nQuads last frame: {}",
+                    self.renderer.atlas_glyphs(),
+                    self.renderer.atlas_usage() * 100.0,
+                    self.renderer.glyph_count()
+                ),
+                TextAnchor::Screen([8.0, 8.0]),
+            )
+            .with_size(14.0),
+        );
+        sections
+    }
+
+    /// Draw the labels over `clear_color` into `target`, a view of a
+    /// texture of the given size
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        let sections = self.sections();
+        self.error = self
+            .renderer
+            .prepare(device, queue, &sections, (width, height), &self.view_proj)
+            .err();
+
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Text Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Text Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            self.renderer.render(&mut render_pass);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn font() -> FontArc {
+        FontArc::try_from_slice(DEFAULT_FONT).unwrap()
+    }
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(TEXT_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<GlyphInstance>(), 52);
+        assert_eq!(std::mem::size_of::<Uniforms>(), 16);
+    }
+
+    #[test]
+    fn test_layout_advances_and_breaks_lines() {
+        let font = font();
+        let (glyphs, block) = layout(&font, "ab\ncd", 20.0);
+        assert_eq!(glyphs.len(), 4);
+        assert!(glyphs[1].origin[0] > glyphs[0].origin[0]);
+        assert_eq!(glyphs[0].origin[1], glyphs[1].origin[1]);
+        assert_eq!(glyphs[2].origin[0], 0.0);
+        assert!(glyphs[2].origin[1] > glyphs[0].origin[1]);
+
+        let (_, line) = layout(&font, "ab", 20.0);
+        assert_eq!(block[0], line[0]);
+        assert!(block[1] > line[1] * 1.5);
+        // Monospaced
+        let (_, wide) = layout(&font, "abcd", 20.0);
+        assert!((wide[0] - 2.0 * line[0]).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_anchor_position() {
+        let screen = anchor_position(
+            TextAnchor::Screen([5.0, 6.0]),
+            [10.0, 10.0],
+            &MAT4_IDENTITY,
+            [100.0, 100.0],
+        );
+        assert_eq!(screen, Some(([5.0, 6.0], 0.0)));
+
+        let center = anchor_position(
+            TextAnchor::World([0.0, 0.0, 0.5]),
+            [10.0, 20.0],
+            &MAT4_IDENTITY,
+            [100.0, 100.0],
+        );
+        assert_eq!(center, Some(([45.0, 40.0], 0.5)));
+
+        let behind = anchor_position(
+            TextAnchor::World([0.0, 0.0, -0.5]),
+            [10.0, 20.0],
+            &MAT4_IDENTITY,
+            [100.0, 100.0],
+        );
+        assert_eq!(behind, None);
+    }
+
+    #[test]
+    fn test_shelf_packer() {
+        let mut packer = ShelfPacker::default();
+        assert_eq!(packer.allocate(10, 20), Some([0, 0]));
+        assert_eq!(packer.allocate(10, 5), Some([11, 0]));
+        assert_eq!(packer.allocate(ATLAS_SIZE - 5, 5), Some([0, 21]));
+        assert_eq!(packer.allocate(ATLAS_SIZE, 5), None);
+        assert!(packer.usage() > 0.0);
+        assert_eq!(packer.allocate(10, ATLAS_SIZE), None);
+    }
+
+    #[test]
+    fn test_section_builder() {
+        let section = TextSection::new("hi", TextAnchor::Screen([0.0; 2]))
+            .with_size(24.0)
+            .with_color([1.0, 0.0, 0.0, 1.0])
+            .with_offset([2.0, 3.0]);
+        assert_eq!(section.size, 24.0);
+        assert_eq!(section.color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(section.offset, [2.0, 3.0]);
+    }
+}
@@ -507,6 +507,7 @@ pub fn load_texture_from_bytes(
 ) -> Result<(Texture, u32, u32), String> {
     use image::GenericImageView;
 
+    crate::cpu_scope!("Load texture");
     // Decode the image
     let img =
         image::load_from_memory(bytes).map_err(|e| format!("Failed to decode image: {}", e))?;
@@ -1,8 +1,82 @@
+use crate::asset_loader::{
+    spawn_image_decode, spawn_svg_rasterize, DecodedImage, DEFAULT_UPLOAD_BUDGET,
+};
+use crate::capability_snapshot::ProjectDescriptor;
+use crate::clipboard_image::{self, BASE_DPI};
+use crate::device_recovery::ResourceRecreate;
+use crate::i18n::{tr, tr_args};
+use crate::job_system::{Job, JobError};
+use crate::mip_debug::MipDebugPreview;
+use crate::operation_errors::OperationErrors;
+use crate::procedural_texture::{self, BakedTexture, ProceduralSettings};
+use crate::state::parse_variant;
+use crate::svg;
 use crate::texture_preview::TexturePreviewState;
 use crate::tooltip::{property, texture_usage, TooltipExt};
-use image::GenericImageView;
+use base64::prelude::*;
 use wgpu::{TextureDimension, TextureFormat, TextureUsages};
 
+/// Largest side of the procedural pattern drawn in the preview, which is
+/// regenerated whenever a setting changes
+const PROCEDURAL_PREVIEW_SIZE: u32 = 256;
+
+/// Operation name of uploading a baked texture in [`OperationErrors`]
+const BAKE_TEXTURE: &str = "bake_texture";
+
+/// Operation name of creating the preview's texture in [`OperationErrors`]
+const PREVIEW_TEXTURE: &str = "preview_texture";
+
+/// Operation name of building the mip level view in [`OperationErrors`]
+#[cfg(not(target_arch = "wasm32"))]
+const MIP_DEBUG: &str = "mip_debug";
+
+/// Formats offered in the format selector, used to restore saved state
+const SELECTABLE_FORMATS: [TextureFormat; 43] = [
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::Rgba16Float,
+    TextureFormat::Rgba32Float,
+    TextureFormat::Rgb10a2Unorm,
+    TextureFormat::R8Unorm,
+    TextureFormat::R8Snorm,
+    TextureFormat::R8Uint,
+    TextureFormat::R8Sint,
+    TextureFormat::R16Uint,
+    TextureFormat::R16Sint,
+    TextureFormat::R16Float,
+    TextureFormat::Rg8Unorm,
+    TextureFormat::Rg8Snorm,
+    TextureFormat::Rg8Uint,
+    TextureFormat::Rg8Sint,
+    TextureFormat::Rg16Uint,
+    TextureFormat::Rg16Sint,
+    TextureFormat::Rg16Float,
+    TextureFormat::Rgba16Uint,
+    TextureFormat::Rgba16Sint,
+    TextureFormat::Rgba32Uint,
+    TextureFormat::Rgba32Sint,
+    TextureFormat::Depth32Float,
+    TextureFormat::Depth24Plus,
+    TextureFormat::Depth24PlusStencil8,
+    TextureFormat::Stencil8,
+    TextureFormat::Bc1RgbaUnorm,
+    TextureFormat::Bc1RgbaUnormSrgb,
+    TextureFormat::Bc2RgbaUnorm,
+    TextureFormat::Bc2RgbaUnormSrgb,
+    TextureFormat::Bc3RgbaUnorm,
+    TextureFormat::Bc3RgbaUnormSrgb,
+    TextureFormat::Bc4RUnorm,
+    TextureFormat::Bc4RSnorm,
+    TextureFormat::Bc5RgUnorm,
+    TextureFormat::Bc5RgSnorm,
+    TextureFormat::Bc6hRgbUfloat,
+    TextureFormat::Bc6hRgbFloat,
+    TextureFormat::Bc7RgbaUnorm,
+    TextureFormat::Bc7RgbaUnormSrgb,
+];
+
 /// UI panel for creating and configuring GPU textures
 pub struct TexturePanel {
     /// Label input text
@@ -35,12 +109,53 @@ pub struct TexturePanel {
     loaded_texture_data: Option<Vec<u8>>,
     /// Loaded texture dimensions
     loaded_texture_dimensions: Option<(u32, u32)>,
+    /// Resolution recorded in the loaded image, if any
+    loaded_texture_dpi: Option<f32>,
+    /// Rasterization settings while the loaded image is an SVG
+    svg_raster: Option<SvgRaster>,
+    /// The loaded image decoded to RGBA, for the previews
+    decoded_image: Option<DecodedImage>,
+    /// Background decode of dropped image bytes
+    pending_decode: Option<Job<Result<DecodedImage, String>>>,
+    /// Bytes being decoded, kept as the loaded data once they decode
+    pending_bytes: Option<Vec<u8>>,
+    /// Fraction of the loaded image written to the preview, while unfinished
+    upload_progress: Option<f32>,
     /// File load message
     file_load_message: Option<String>,
     /// Texture preview rendering state
     preview_state: Option<TexturePreviewState>,
     /// Whether preview is enabled
     show_preview: bool,
+    /// Mip level view of the texture with the configured mip count
+    mip_debug: Option<MipDebugPreview>,
+    /// Whether the mip level view is shown below the preview
+    show_mip_debug: bool,
+    /// Width, height and mip count the mip level view was built with
+    mip_debug_source: Option<(u32, u32, u32)>,
+    /// Settings of the procedural generator
+    procedural: ProceduralSettings,
+    /// Settings and size the preview's procedural pattern was generated with
+    procedural_source: Option<(ProceduralSettings, u32, u32)>,
+    /// Background generation of the texture being baked
+    bake_job: Option<Job<DecodedImage>>,
+    /// Last texture baked on the GPU, offered to bind groups
+    baked: Option<BakedTexture>,
+    /// Whether the loaded image is the last bake rather than a file
+    loaded_from_generator: bool,
+    /// Device errors from creating the baked and preview textures
+    gpu_errors: OperationErrors,
+}
+
+/// Size a loaded SVG is rasterized at, edited before re-rasterizing
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SvgRaster {
+    /// Size the SVG declares, which sets the aspect ratio
+    intrinsic: (f32, f32),
+    width: u32,
+    height: u32,
+    /// Change the height with the width and the other way around
+    keep_aspect: bool,
 }
 
 impl Default for TexturePanel {
@@ -70,9 +185,24 @@ impl TexturePanel {
             success_message: None,
             loaded_texture_data: None,
             loaded_texture_dimensions: None,
+            loaded_texture_dpi: None,
+            svg_raster: None,
+            decoded_image: None,
+            pending_decode: None,
+            pending_bytes: None,
+            upload_progress: None,
             file_load_message: None,
             preview_state: None,
             show_preview: true,
+            mip_debug: None,
+            show_mip_debug: false,
+            mip_debug_source: None,
+            procedural: ProceduralSettings::default(),
+            procedural_source: None,
+            bake_job: None,
+            baked: None,
+            loaded_from_generator: false,
+            gpu_errors: OperationErrors::new(),
         }
     }
 
@@ -82,7 +212,7 @@ impl TexturePanel {
         let width = match self.width_input.parse::<u32>() {
             Ok(w) if w > 0 => w,
             _ => {
-                self.validation_error = Some("Width must be a positive number".to_string());
+                self.validation_error = Some(tr("texture-width-invalid"));
                 self.success_message = None;
                 return false;
             }
@@ -91,7 +221,7 @@ impl TexturePanel {
         let height = match self.height_input.parse::<u32>() {
             Ok(h) if h > 0 => h,
             _ => {
-                self.validation_error = Some("Height must be a positive number".to_string());
+                self.validation_error = Some(tr("texture-height-invalid"));
                 self.success_message = None;
                 return false;
             }
@@ -100,8 +230,7 @@ impl TexturePanel {
         let depth = match self.depth_input.parse::<u32>() {
             Ok(d) if d > 0 => d,
             _ => {
-                self.validation_error =
-                    Some("Depth/array layers must be a positive number".to_string());
+                self.validation_error = Some(tr("texture-depth-invalid"));
                 self.success_message = None;
                 return false;
             }
@@ -110,7 +239,7 @@ impl TexturePanel {
         let mip_levels = match self.mip_levels_input.parse::<u32>() {
             Ok(m) if m > 0 => m,
             _ => {
-                self.validation_error = Some("Mip levels must be a positive number".to_string());
+                self.validation_error = Some(tr("texture-mip-levels-invalid"));
                 self.success_message = None;
                 return false;
             }
@@ -119,8 +248,7 @@ impl TexturePanel {
         let sample_count = match self.sample_count_input.parse::<u32>() {
             Ok(s) if [1, 2, 4, 8, 16, 32].contains(&s) => s,
             _ => {
-                self.validation_error =
-                    Some("Sample count must be 1, 2, 4, 8, 16, or 32".to_string());
+                self.validation_error = Some(tr("texture-sample-count-invalid"));
                 self.success_message = None;
                 return false;
             }
@@ -129,7 +257,7 @@ impl TexturePanel {
         // Build usage flags
         let usage = self.build_usage_flags();
         if usage.is_empty() {
-            self.validation_error = Some("At least one usage flag must be selected".to_string());
+            self.validation_error = Some(tr("texture-usage-empty"));
             self.success_message = None;
             return false;
         }
@@ -137,13 +265,12 @@ impl TexturePanel {
         // Validate dimension constraints
         if self.selected_dimension == TextureDimension::D1 {
             if height != 1 {
-                self.validation_error = Some("1D textures must have height = 1".to_string());
+                self.validation_error = Some(tr("texture-1d-height"));
                 self.success_message = None;
                 return false;
             }
             if depth != 1 {
-                self.validation_error =
-                    Some("1D textures must have depth/array layers = 1".to_string());
+                self.validation_error = Some(tr("texture-1d-depth"));
                 self.success_message = None;
                 return false;
             }
@@ -153,9 +280,14 @@ impl TexturePanel {
         let max_dimension = width.max(height);
         let max_mip_levels = (max_dimension as f32).log2().floor() as u32 + 1;
         if mip_levels > max_mip_levels {
-            self.validation_error = Some(format!(
-                "Mip levels ({}) exceeds maximum ({}) for {}x{} texture",
-                mip_levels, max_mip_levels, width, height
+            self.validation_error = Some(tr_args(
+                "texture-mip-levels-too-many",
+                &[
+                    ("levels", &mip_levels),
+                    ("max", &max_mip_levels),
+                    ("width", &width),
+                    ("height", &height),
+                ],
             ));
             self.success_message = None;
             return false;
@@ -164,13 +296,12 @@ impl TexturePanel {
         // Validate multisampling
         if sample_count > 1 {
             if mip_levels > 1 {
-                self.validation_error =
-                    Some("Multisampled textures cannot have mip levels > 1".to_string());
+                self.validation_error = Some(tr("texture-multisampled-mips"));
                 self.success_message = None;
                 return false;
             }
             if self.selected_dimension != TextureDimension::D2 {
-                self.validation_error = Some("Only 2D textures can be multisampled".to_string());
+                self.validation_error = Some(tr("texture-multisampled-dimension"));
                 self.success_message = None;
                 return false;
             }
@@ -201,36 +332,531 @@ impl TexturePanel {
         usage
     }
 
+    /// The configured texture, for checks against a virtual device
+    ///
+    /// `None` while a dimension or the sample count is not a positive number.
+    pub fn project_descriptor(&self) -> Option<ProjectDescriptor> {
+        let parse = |input: &str| input.parse::<u32>().ok().filter(|&v| v > 0);
+        Some(ProjectDescriptor::Texture {
+            label: self.label_input.clone(),
+            format: self.selected_format,
+            dimension: self.selected_dimension,
+            size: wgpu::Extent3d {
+                width: parse(&self.width_input)?,
+                height: parse(&self.height_input)?,
+                depth_or_array_layers: parse(&self.depth_input)?,
+            },
+            usage: self.build_usage_flags(),
+            sample_count: parse(&self.sample_count_input)?,
+        })
+    }
+
     /// Handle file loading from bytes
+    ///
+    /// The image is decoded on a background job; the panel picks up the
+    /// result on a later frame, or in [`finish_loading`](Self::finish_loading).
     pub fn load_from_bytes(&mut self, bytes: Vec<u8>) {
-        // Try to decode the image to get dimensions
-        match image::load_from_memory(&bytes) {
-            Ok(img) => {
-                let dimensions = img.dimensions();
-                self.loaded_texture_data = Some(bytes);
+        self.pending_decode = Some(spawn_image_decode("Texture image", bytes.clone()));
+        self.pending_bytes = Some(bytes);
+        self.file_load_message = None;
+        self.validation_error = None;
+    }
+
+    /// Whether dropped image bytes are still being decoded
+    pub fn is_loading(&self) -> bool {
+        self.pending_decode.is_some()
+    }
+
+    /// Apply the decoded image if the background decode has finished
+    pub fn poll_loading(&mut self) {
+        let Some(job) = &mut self.pending_decode else {
+            return;
+        };
+        if let Some(outcome) = job.poll() {
+            self.pending_decode = None;
+            self.apply_decoded(outcome);
+        }
+    }
+
+    /// Wait for the background decode and apply its result
+    pub fn finish_loading(&mut self) {
+        if let Some(job) = self.pending_decode.take() {
+            self.apply_decoded(job.wait());
+        }
+    }
+
+    /// Stop decoding dropped image bytes, keeping the current image
+    pub fn cancel_loading(&mut self) {
+        self.pending_decode = None;
+        self.pending_bytes = None;
+    }
+
+    fn apply_decoded(&mut self, outcome: Result<Result<DecodedImage, String>, JobError>) {
+        let bytes = self.pending_bytes.take();
+        match outcome
+            .map_err(|e| e.to_string())
+            .and_then(|decoded| decoded)
+        {
+            Ok(image) => {
+                let dimensions = (image.width, image.height);
+                self.loaded_texture_dpi = bytes.as_deref().and_then(clipboard_image::image_dpi);
+                let keep_aspect = self.svg_raster.is_none_or(|raster| raster.keep_aspect);
+                self.svg_raster = match bytes.as_deref() {
+                    Some(source) if svg::is_svg(source) => Some(SvgRaster {
+                        intrinsic: svg::intrinsic_size(source)
+                            .unwrap_or((dimensions.0 as f32, dimensions.1 as f32)),
+                        width: dimensions.0,
+                        height: dimensions.1,
+                        keep_aspect,
+                    }),
+                    _ => None,
+                };
+                self.loaded_texture_data = bytes;
                 self.loaded_texture_dimensions = Some(dimensions);
+                self.decoded_image = Some(image);
+                self.mip_debug_source = None;
                 self.width_input = dimensions.0.to_string();
                 self.height_input = dimensions.1.to_string();
-                self.file_load_message = Some(format!(
-                    "✓ Image loaded successfully: {}x{} pixels",
-                    dimensions.0, dimensions.1
+                self.file_load_message = Some(tr_args(
+                    "texture-file-loaded",
+                    &[("width", &dimensions.0), ("height", &dimensions.1)],
                 ));
                 self.validation_error = None;
             }
             Err(e) => {
                 self.file_load_message = None;
-                self.validation_error = Some(format!("Failed to load image: {}", e));
+                self.validation_error = Some(tr_args("texture-file-load-failed", &[("error", &e)]));
+            }
+        }
+    }
+
+    /// Progress of the decode or the preview upload, with a cancel button
+    fn loading_ui(&mut self, ui: &mut egui::Ui) {
+        if let Some(job) = &self.pending_decode {
+            let status = job.status();
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(if status.running {
+                    tr("texture-file-decoding")
+                } else {
+                    tr("texture-file-decode-waiting")
+                });
+                if ui.small_button(tr("texture-file-cancel")).clicked() {
+                    self.cancel_loading();
+                }
+            });
+        }
+        if let Some(fraction) = self.upload_progress {
+            ui.add(
+                egui::ProgressBar::new(fraction)
+                    .show_percentage()
+                    .text(tr("texture-file-uploading")),
+            );
+        }
+    }
+
+    /// Load the image on the OS clipboard, e.g. a screenshot
+    ///
+    /// `dpi` is the resolution the image was captured at, normally that of
+    /// the display; clipboard images don't record one. Returns the image
+    /// size, or the error also shown in the panel.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn paste_from_clipboard(&mut self, dpi: Option<f32>) -> Result<(u32, u32), String> {
+        match clipboard_image::read_clipboard_image() {
+            Ok(image) => self.paste_image(image, dpi),
+            Err(e) => {
+                self.file_load_message = None;
+                self.validation_error =
+                    Some(tr_args("texture-file-paste-failed", &[("error", &e)]));
+                Err(e.to_string())
             }
         }
     }
 
+    /// Load an image taken from the clipboard
+    ///
+    /// It is kept as PNG like loaded files, with `dpi` recorded in it so the
+    /// resolution survives saving the state.
+    pub fn paste_image(
+        &mut self,
+        image: DecodedImage,
+        dpi: Option<f32>,
+    ) -> Result<(u32, u32), String> {
+        match self.load_image(image, dpi) {
+            Ok(dimensions) => {
+                self.file_load_message = Some(tr_args(
+                    "texture-file-pasted",
+                    &[("width", &dimensions.0), ("height", &dimensions.1)],
+                ));
+                Ok(dimensions)
+            }
+            Err(e) => {
+                self.validation_error =
+                    Some(tr_args("texture-file-paste-failed", &[("error", &e)]));
+                Err(e)
+            }
+        }
+    }
+
+    /// Load an image made in the playground, kept as PNG like loaded files
+    fn load_image(&mut self, image: DecodedImage, dpi: Option<f32>) -> Result<(u32, u32), String> {
+        let bytes = clipboard_image::encode_png(&image, dpi).map_err(|e| e.to_string())?;
+        let dimensions = (image.width, image.height);
+        self.clear_loaded_texture();
+        self.pending_bytes = Some(bytes);
+        self.apply_decoded(Ok(Ok(image)));
+        Ok(dimensions)
+    }
+
+    /// Resolution recorded in the loaded image
+    pub fn get_loaded_texture_dpi(&self) -> Option<f32> {
+        self.loaded_texture_dpi
+    }
+
+    /// Resolution of the loaded image, with a button applying its size in
+    /// logical points when it isn't made at the base resolution
+    fn dpi_ui(&mut self, ui: &mut egui::Ui) {
+        let (Some(dpi), Some((width, height))) =
+            (self.loaded_texture_dpi, self.loaded_texture_dimensions)
+        else {
+            return;
+        };
+        ui.label(tr_args(
+            "texture-dpi",
+            &[
+                ("dpi", &dpi),
+                ("scale", &format!("{:.2}", clipboard_image::scale_factor(dpi))),
+            ],
+        ));
+        if dpi != BASE_DPI {
+            let (logical_width, logical_height) = clipboard_image::logical_size(width, height, dpi);
+            if ui
+                .button(tr_args(
+                    "texture-dpi-logical-size",
+                    &[("width", &logical_width), ("height", &logical_height)],
+                ))
+                .on_hover_text(tr_args(
+                    "texture-dpi-logical-size-hover",
+                    &[("dpi", &BASE_DPI)],
+                ))
+                .clicked()
+            {
+                self.width_input = logical_width.to_string();
+                self.height_input = logical_height.to_string();
+            }
+        }
+    }
+
+    /// Draw the loaded SVG again at `width` by `height` pixels
+    ///
+    /// Like loading, this runs in the background. Does nothing unless the
+    /// loaded image is an SVG.
+    pub fn rasterize_svg(&mut self, width: u32, height: u32) {
+        let Some(bytes) = self
+            .loaded_texture_data
+            .clone()
+            .filter(|bytes| svg::is_svg(bytes))
+        else {
+            return;
+        };
+        self.start_svg_rasterize(bytes, width, height);
+    }
+
+    fn start_svg_rasterize(&mut self, bytes: Vec<u8>, width: u32, height: u32) {
+        self.pending_decode = Some(spawn_svg_rasterize(
+            "Texture SVG",
+            bytes.clone(),
+            width,
+            height,
+        ));
+        self.pending_bytes = Some(bytes);
+        self.file_load_message = None;
+        self.validation_error = None;
+    }
+
+    /// Size the loaded SVG is rasterized at, `None` for other images
+    pub fn get_svg_raster_size(&self) -> Option<(u32, u32)> {
+        self.svg_raster.map(|raster| (raster.width, raster.height))
+    }
+
+    /// Size fields and scale shortcuts for re-rasterizing a loaded SVG
+    fn svg_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(mut raster) = self.svg_raster else {
+            return;
+        };
+        ui.add_space(5.0);
+        ui.label(tr_args(
+            "texture-svg-info",
+            &[("width", &raster.intrinsic.0), ("height", &raster.intrinsic.1)],
+        ));
+        let mut rasterize = false;
+        ui.horizontal(|ui| {
+            ui.label(tr("texture-svg-rasterize-at"));
+            let range = 1..=svg::MAX_RASTER_SIZE;
+            if ui
+                .add(egui::DragValue::new(&mut raster.width).range(range.clone()))
+                .changed()
+                && raster.keep_aspect
+            {
+                raster.height = svg::height_for_width(raster.intrinsic, raster.width);
+            }
+            ui.label("x");
+            if ui
+                .add(egui::DragValue::new(&mut raster.height).range(range))
+                .changed()
+                && raster.keep_aspect
+            {
+                raster.width = svg::width_for_height(raster.intrinsic, raster.height);
+            }
+            ui.checkbox(&mut raster.keep_aspect, tr("texture-svg-keep-aspect"));
+        });
+        ui.horizontal(|ui| {
+            for scale in [0.5, 1.0, 2.0, 4.0] {
+                if ui.small_button(format!("{}x", scale)).clicked() {
+                    (raster.width, raster.height) =
+                        svg::raster_size((raster.intrinsic.0 * scale, raster.intrinsic.1 * scale));
+                    rasterize = true;
+                }
+            }
+            rasterize |= ui
+                .add_enabled(
+                    !self.is_loading(),
+                    egui::Button::new(tr("texture-svg-rasterize")),
+                )
+                .on_hover_text(tr("texture-svg-rasterize-hover"))
+                .clicked();
+        });
+        self.svg_raster = Some(raster);
+        if rasterize {
+            self.rasterize_svg(raster.width, raster.height);
+        }
+    }
+
+    /// Generate the procedural pattern at the Width and Height fields
+    ///
+    /// Like loading, this runs in the background. The result becomes the
+    /// loaded image and, when [`poll_bake`](Self::poll_bake) is given a
+    /// device, a GPU texture.
+    pub fn bake(&mut self) {
+        let side = |input: &str| {
+            input
+                .parse::<u32>()
+                .ok()
+                .filter(|side| (1..=procedural_texture::MAX_BAKE_SIZE).contains(side))
+        };
+        let (Some(width), Some(height)) = (side(&self.width_input), side(&self.height_input))
+        else {
+            self.validation_error = Some(tr_args(
+                "texture-bake-size",
+                &[("max", &procedural_texture::MAX_BAKE_SIZE)],
+            ));
+            return;
+        };
+        self.bake_job = Some(procedural_texture::spawn_generate(
+            self.procedural.clone(),
+            width,
+            height,
+        ));
+        self.validation_error = None;
+    }
+
+    /// Whether a procedural texture is being generated
+    pub fn is_baking(&self) -> bool {
+        self.bake_job.is_some()
+    }
+
+    /// Apply the baked texture if its generation has finished
+    pub fn poll_bake(&mut self, device: Option<&wgpu::Device>, queue: Option<&wgpu::Queue>) {
+        let Some(job) = &mut self.bake_job else {
+            return;
+        };
+        if let Some(outcome) = job.poll() {
+            self.bake_job = None;
+            self.apply_bake(outcome, device, queue);
+        }
+    }
+
+    /// Wait for the texture being baked and apply it
+    pub fn finish_bake(&mut self, device: Option<&wgpu::Device>, queue: Option<&wgpu::Queue>) {
+        if let Some(job) = self.bake_job.take() {
+            self.apply_bake(job.wait(), device, queue);
+        }
+    }
+
+    fn apply_bake(
+        &mut self,
+        outcome: Result<DecodedImage, JobError>,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+    ) {
+        let image = match outcome {
+            Ok(image) => image,
+            Err(e) => {
+                self.validation_error = Some(tr_args("texture-bake-failed", &[("error", &e)]));
+                return;
+            }
+        };
+        let name = self.procedural.generator.name();
+        // Normal maps hold vectors, which must not be sRGB decoded
+        let format = if self.procedural.normal_map {
+            TextureFormat::Rgba8Unorm
+        } else {
+            TextureFormat::Rgba8UnormSrgb
+        };
+        self.upload_baked(&image, format, device, queue);
+        match self.load_image(image, None) {
+            Ok((width, height)) => {
+                self.loaded_from_generator = true;
+                self.file_load_message = Some(tr_args(
+                    "texture-baked",
+                    &[("pattern", &name), ("width", &width), ("height", &height)],
+                ));
+            }
+            Err(e) => {
+                self.validation_error = Some(tr_args("texture-bake-failed", &[("error", &e)]))
+            }
+        }
+    }
+
+    /// Replace the loaded image with a normal map of its brightness
+    pub fn normal_map_from_image(
+        &mut self,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+    ) {
+        let Some(image) = &self.decoded_image else {
+            return;
+        };
+        let heights = procedural_texture::luminance_heights(&image.rgba);
+        let normal_map = DecodedImage {
+            width: image.width,
+            height: image.height,
+            rgba: procedural_texture::height_to_normal_map(
+                &heights,
+                image.width,
+                image.height,
+                self.procedural.normal_strength,
+            ),
+        };
+        self.upload_baked(&normal_map, TextureFormat::Rgba8Unorm, device, queue);
+        let dpi = self.loaded_texture_dpi;
+        match self.load_image(normal_map, dpi) {
+            Ok((width, height)) => {
+                self.file_load_message = Some(tr_args(
+                    "texture-normal-map-made",
+                    &[("width", &width), ("height", &height)],
+                ));
+            }
+            Err(e) => {
+                self.validation_error = Some(tr_args("texture-normal-map-failed", &[("error", &e)]))
+            }
+        }
+    }
+
+    /// Upload `image` as the baked texture, labelled like the configured one
+    fn upload_baked(
+        &mut self,
+        image: &DecodedImage,
+        format: TextureFormat,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+    ) {
+        let (Some(device), Some(queue)) = (device, queue) else {
+            return;
+        };
+        let label = if self.label_input.is_empty() {
+            format!("Baked {}", self.procedural.generator.name())
+        } else {
+            self.label_input.clone()
+        };
+        let baked = self.gpu_errors.scoped(device, BAKE_TEXTURE, || {
+            BakedTexture::new(device, queue, image, &label, format)
+        });
+        // A texture the device rejected can't be bound
+        self.baked = (!self.gpu_errors.has_error(BAKE_TEXTURE)).then_some(baked);
+    }
+
+    /// Last texture baked on the GPU, to bind in bind groups
+    pub fn baked_texture(&self) -> Option<&BakedTexture> {
+        self.baked.as_ref()
+    }
+
+    /// Generator settings with buttons baking the pattern into a GPU texture
+    /// or turning the loaded image into a normal map
+    fn procedural_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: Option<&wgpu::Device>,
+        queue: Option<&wgpu::Queue>,
+    ) {
+        ui.group(|ui| {
+            ui.heading(tr("texture-procedural-heading"));
+            ui.label(tr("texture-procedural-intro"));
+            ui.add_space(5.0);
+
+            if procedural_texture::settings_ui(ui, &mut self.procedural)
+                && self.loaded_from_generator
+            {
+                // Preview the edited pattern instead of the last bake
+                self.clear_loaded_texture();
+            }
+
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!self.is_baking(), egui::Button::new(tr("texture-bake")))
+                    .on_hover_text(tr("texture-bake-hover"))
+                    .clicked()
+                {
+                    self.bake();
+                }
+                if self.decoded_image.is_some()
+                    && ui
+                        .button(tr("texture-normal-map"))
+                        .on_hover_text(tr("texture-normal-map-hover"))
+                        .clicked()
+                {
+                    self.normal_map_from_image(device, queue);
+                }
+            });
+
+            self.gpu_errors.ui(ui, BAKE_TEXTURE);
+
+            if self.is_baking() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr("texture-baking"));
+                });
+            }
+            if let Some(baked) = &self.baked {
+                let (width, height) = baked.size();
+                ui.label(tr_args(
+                    "texture-baked-info",
+                    &[
+                        ("label", &baked.label),
+                        ("width", &width),
+                        ("height", &height),
+                        ("format", &format!("{:?}", baked.format())),
+                    ],
+                ));
+            }
+        });
+    }
+
     /// Clear loaded texture data
     pub fn clear_loaded_texture(&mut self) {
+        self.cancel_loading();
         self.loaded_texture_data = None;
         self.loaded_texture_dimensions = None;
+        self.loaded_texture_dpi = None;
+        self.svg_raster = None;
+        self.decoded_image = None;
+        self.upload_progress = None;
         self.file_load_message = None;
         // Clear preview state so it regenerates
         self.preview_state = None;
+        self.mip_debug_source = None;
+        self.loaded_from_generator = false;
     }
 
     /// Get loaded texture data
@@ -280,6 +906,45 @@ impl TexturePanel {
         self.ui_impl_wasm(ui, device, queue);
     }
 
+    /// Mip level view of the loaded image, or the checkerboard, with as
+    /// many levels as the Mip Levels field asks for
+    #[cfg(not(target_arch = "wasm32"))]
+    fn mip_debug_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut egui_wgpu::Renderer,
+    ) {
+        let mip_levels = self.mip_levels_input.parse::<u32>().unwrap_or(1).max(1);
+        let (width, height) = self.loaded_texture_dimensions.unwrap_or((
+            self.width_input.parse::<u32>().unwrap_or(256).max(1),
+            self.height_input.parse::<u32>().unwrap_or(256).max(1),
+        ));
+        let preview = self
+            .mip_debug
+            .get_or_insert_with(|| MipDebugPreview::new(device));
+        if self.mip_debug_source != Some((width, height, mip_levels)) {
+            self.gpu_errors
+                .scoped(device, MIP_DEBUG, || match &self.decoded_image {
+                    Some(image) => {
+                        preview.set_image(device, queue, &image.rgba, width, height, mip_levels)
+                    }
+                    None => {
+                        let rgba = crate::mip_debug::procedural_texture(width, height);
+                        preview.set_image(device, queue, &rgba, width, height, mip_levels);
+                    }
+                });
+            self.mip_debug_source = Some((width, height, mip_levels));
+        }
+        self.gpu_errors.ui(ui, MIP_DEBUG);
+
+        preview.ui(ui, device, queue, renderer);
+        if preview.level_count() == 1 {
+            ui.colored_label(egui::Color32::YELLOW, tr("texture-preview-single-mip"));
+        }
+    }
+
     /// Internal implementation of texture configuration UI (Native version)
     #[cfg(not(target_arch = "wasm32"))]
     fn ui_impl_native(
@@ -289,43 +954,47 @@ impl TexturePanel {
         queue: Option<&wgpu::Queue>,
         #[allow(unused_variables)] renderer: Option<&mut egui_wgpu::Renderer>,
     ) {
+        self.poll_loading();
+        self.poll_bake(device, queue);
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("🖼️ Texture Configuration");
-            ui.label("Configure and create GPU textures with custom parameters.");
+            ui.heading(tr("texture-heading"));
+            ui.label(tr("texture-intro"));
             ui.add_space(10.0);
 
 
             // Texture Properties
             ui.group(|ui| {
-                ui.heading("Texture Properties");
+                ui.heading(tr("texture-properties"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("texture_properties")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Label:")
-                            .webgpu_tooltip("Optional label for debugging and identification", Some("#dom-gpuobjectbase-label"));
+                        ui.label(tr("texture-label")).webgpu_tooltip(
+                            &tr("texture-label-tooltip"),
+                            Some("#dom-gpuobjectbase-label"),
+                        );
                         ui.text_edit_singleline(&mut self.label_input);
                         ui.end_row();
 
-                        property::TEXTURE_WIDTH.apply(ui.label("Width:"));
+                        property::TEXTURE_WIDTH.apply(ui.label(tr("texture-width")));
                         ui.text_edit_singleline(&mut self.width_input);
                         ui.end_row();
 
-                        property::TEXTURE_HEIGHT.apply(ui.label("Height:"));
+                        property::TEXTURE_HEIGHT.apply(ui.label(tr("texture-height")));
                         ui.text_edit_singleline(&mut self.height_input);
                         ui.end_row();
 
-                        property::TEXTURE_DEPTH.apply(ui.label("Depth/Array Layers:"));
+                        property::TEXTURE_DEPTH.apply(ui.label(tr("texture-depth")));
                         ui.text_edit_singleline(&mut self.depth_input);
                         ui.end_row();
 
-                        property::TEXTURE_MIP_LEVELS.apply(ui.label("Mip Levels:"));
+                        property::TEXTURE_MIP_LEVELS.apply(ui.label(tr("texture-mip-levels")));
                         ui.text_edit_singleline(&mut self.mip_levels_input);
                         ui.end_row();
 
-                        property::TEXTURE_SAMPLE_COUNT.apply(ui.label("Sample Count:"));
+                        property::TEXTURE_SAMPLE_COUNT.apply(ui.label(tr("texture-sample-count")));
                         ui.text_edit_singleline(&mut self.sample_count_input);
                         ui.end_row();
                     });
@@ -335,7 +1004,7 @@ impl TexturePanel {
 
             // Texture Dimension
             ui.group(|ui| {
-                ui.heading("Texture Dimension");
+                ui.heading(tr("texture-dimension-heading"));
                 ui.add_space(5.0);
 
                 ui.horizontal(|ui| {
@@ -349,13 +1018,13 @@ impl TexturePanel {
 
             // Texture Format
             ui.group(|ui| {
-                ui.heading("Texture Format");
+                ui.heading(tr("texture-format-heading"));
                 ui.add_space(5.0);
 
-                egui::ComboBox::from_label("Format")
+                egui::ComboBox::from_label(tr("texture-format"))
                     .selected_text(format!("{:?}", self.selected_format))
                     .show_ui(ui, |ui| {
-                        ui.label("Color Formats");
+                        ui.label(tr("texture-format-color"));
                         ui.separator();
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Rgba8Unorm);
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Rgba8UnormSrgb);
@@ -384,7 +1053,7 @@ impl TexturePanel {
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Rgba32Sint);
 
                         ui.add_space(5.0);
-                        ui.label("Depth/Stencil Formats");
+                        ui.label(tr("texture-format-depth-stencil"));
                         ui.separator();
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Depth32Float);
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Depth24Plus);
@@ -392,7 +1061,7 @@ impl TexturePanel {
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Stencil8);
 
                         ui.add_space(5.0);
-                        ui.label("Compressed Formats (BC)");
+                        ui.label(tr("texture-format-compressed"));
                         ui.separator();
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Bc1RgbaUnorm);
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Bc1RgbaUnormSrgb);
@@ -415,8 +1084,8 @@ impl TexturePanel {
 
             // Usage Flags
             ui.group(|ui| {
-                ui.heading("Usage Flags");
-                ui.label("Select how the texture will be used (multiple flags can be selected):");
+                ui.heading(tr("texture-usage-heading"));
+                ui.label(tr("texture-usage-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("usage_flags")
@@ -461,27 +1130,36 @@ impl TexturePanel {
 
             // File Loading Section
             ui.group(|ui| {
-                ui.heading("📁 Load Texture from File");
-                ui.label("Load image files (PNG, JPEG) to create textures.");
+                ui.heading(tr("texture-file-heading"));
+                ui.label(tr("texture-file-intro"));
                 ui.add_space(5.0);
 
                 ui.horizontal(|ui| {
                     #[cfg(not(target_arch = "wasm32"))]
                     {
-                        if ui.button("📂 Load Image...").clicked() {
-                            self.file_load_message = Some("Drag and drop an image file onto this window to load it.".to_string());
+                        if ui.button(tr("texture-file-load")).clicked() {
+                            self.file_load_message = Some(tr("texture-file-drop-window"));
+                        }
+                        if ui
+                            .button(tr("texture-file-paste"))
+                            .on_hover_text(tr("texture-file-paste-hover"))
+                            .clicked()
+                        {
+                            // Screenshots are taken at the display's resolution
+                            let dpi = ui.ctx().native_pixels_per_point().map(|scale| scale * BASE_DPI);
+                            let _ = self.paste_from_clipboard(dpi);
                         }
                     }
 
                     #[cfg(target_arch = "wasm32")]
                     {
-                        if ui.button("📂 Load Image...").clicked() {
-                            self.file_load_message = Some("Drag and drop an image file onto the browser window to load it.".to_string());
+                        if ui.button(tr("texture-file-load")).clicked() {
+                            self.file_load_message = Some(tr("texture-file-drop-browser"));
                         }
                     }
 
                     if self.loaded_texture_data.is_some()
-                        && ui.button("🗑️ Clear Loaded Image").clicked()
+                        && ui.button(tr("texture-file-clear")).clicked()
                     {
                         self.clear_loaded_texture();
                     }
@@ -494,32 +1172,50 @@ impl TexturePanel {
                     ui.colored_label(egui::Color32::GREEN, msg);
                 }
 
+                self.loading_ui(ui);
+
                 if let Some((width, height)) = self.loaded_texture_dimensions {
-                    ui.label(format!("📐 Loaded image: {} x {} pixels", width, height));
-                    ui.label("Image dimensions have been applied to Width and Height fields.");
+                    ui.label(tr_args(
+                        "texture-file-loaded-size",
+                        &[("width", &width), ("height", &height)],
+                    ));
+                    ui.label(tr("texture-file-size-applied"));
                 }
+                self.dpi_ui(ui);
+                self.svg_ui(ui);
 
                 ui.add_space(5.0);
-                ui.label("💡 Tip: Drag and drop image files onto the application window to load them.");
+                ui.label(tr("texture-file-tip"));
             });
 
             ui.add_space(15.0);
 
+            self.procedural_ui(ui, device, queue);
+
+            ui.add_space(15.0);
+
             // Preview Section
             if self.show_preview && (self.loaded_texture_data.is_some() || self.width_input.parse::<u32>().is_ok()) {
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.heading("🎨 Texture Preview");
-                        if ui.small_button("✕").on_hover_text("Hide preview").clicked() {
+                        ui.heading(tr("texture-preview-heading"));
+                        if ui
+                            .small_button("✕")
+                            .on_hover_text(tr("texture-preview-hide"))
+                            .clicked()
+                        {
                             self.show_preview = false;
                         }
                     });
                     ui.add_space(5.0);
 
                     if self.loaded_texture_data.is_some() {
-                        ui.label("Preview shows the loaded image texture:");
+                        ui.label(tr("texture-preview-image"));
                     } else {
-                        ui.label("Preview shows a procedural checkerboard texture:");
+                        ui.label(tr_args(
+                            "texture-preview-procedural",
+                            &[("pattern", &self.procedural.generator.name())],
+                        ));
                     }
 
                     ui.add_space(5.0);
@@ -528,7 +1224,7 @@ impl TexturePanel {
                     if let Some(device) = device {
                         if self.preview_state.is_none() {
                             let mut preview = TexturePreviewState::new();
-                            preview.initialize(device);
+                            self.gpu_errors.scoped(device, PREVIEW_TEXTURE, || preview.initialize(device));
                             self.preview_state = Some(preview);
                         }
                     }
@@ -537,30 +1233,30 @@ impl TexturePanel {
                     if let (Some(preview), Some(device), Some(queue)) =
                         (&mut self.preview_state, device, queue)
                     {
-                        if let Some(loaded_data) = &self.loaded_texture_data {
-                            // Display loaded image
-                            if let Some((width, height)) = self.loaded_texture_dimensions {
-                                // Convert image data to RGBA if needed
-                                if let Ok(img) = image::load_from_memory(loaded_data) {
-                                    let rgba = img.to_rgba8();
-                                    let rgba_data = rgba.as_raw();
-
-                                    if !preview.has_texture() || self.file_load_message.is_some() {
-                                        preview.update_from_image_data(device, queue, rgba_data, width, height);
-                                        // Clear the file load message after updating preview
-                                        self.file_load_message = None;
-                                    }
-                                }
+                        if let Some(image) = &self.decoded_image {
+                            // Display loaded image, written a few rows per frame
+                            if !preview.has_texture() || self.file_load_message.is_some() {
+                                self.gpu_errors.scoped(device, PREVIEW_TEXTURE, || {
+                                    preview.begin_image_upload(device, image.clone())
+                                });
+                                // Clear the file load message after updating preview
+                                self.file_load_message = None;
                             }
-                        } else if self.width_input.parse::<u32>().is_ok() && self.height_input.parse::<u32>().is_ok() {
-                            // Generate procedural texture
-                            let width = self.width_input.parse::<u32>().unwrap_or(256);
-                            let height = self.height_input.parse::<u32>().unwrap_or(256);
-
-                            if !preview.has_texture() {
-                                preview.generate_procedural_texture(device, queue, width, height);
+                            self.upload_progress = preview.continue_upload(queue, DEFAULT_UPLOAD_BUDGET);
+                        } else if let (Ok(width), Ok(height)) =
+                            (self.width_input.parse::<u32>(), self.height_input.parse::<u32>())
+                        {
+                            // Generate the procedural pattern again when its settings change
+                            let (width, height) = procedural_texture::preview_size(width, height, PROCEDURAL_PREVIEW_SIZE);
+                            let source = Some((self.procedural.clone(), width, height));
+                            if !preview.has_texture() || self.procedural_source != source {
+                                self.gpu_errors.scoped(device, PREVIEW_TEXTURE, || {
+                                    preview.generate_procedural_texture(device, queue, &self.procedural, width, height)
+                                });
+                                self.procedural_source = source;
                             }
                         }
+                        self.gpu_errors.ui(ui, PREVIEW_TEXTURE);
 
                         // Render preview
                         preview.render(device, queue);
@@ -575,19 +1271,23 @@ impl TexturePanel {
                                     egui::vec2(width as f32, height as f32),
                                 )));
                             }
+
+                            ui.add_space(5.0);
+                            ui.checkbox(&mut self.show_mip_debug, tr("texture-preview-mip-view"))
+                                .on_hover_text(tr("texture-preview-mip-view-hover"));
+                            if self.show_mip_debug {
+                                self.mip_debug_ui(ui, device, queue, renderer);
+                            }
                         }
                     } else if device.is_none() {
-                        ui.colored_label(
-                            egui::Color32::YELLOW,
-                            "⚠ Preview requires GPU device to be initialized"
-                        );
+                        ui.colored_label(egui::Color32::YELLOW, tr("texture-preview-needs-device"));
                     }
                 });
 
                 ui.add_space(15.0);
             } else if self.loaded_texture_data.is_some() || self.width_input.parse::<u32>().is_ok() {
                 // Show button to enable preview
-                if ui.button("🎨 Show Texture Preview").clicked() {
+                if ui.button(tr("texture-preview-show")).clicked() {
                     self.show_preview = true;
                 }
             }
@@ -596,18 +1296,15 @@ impl TexturePanel {
 
             // Validation and Creation
             ui.horizontal(|ui| {
-                if ui.button("🔍 Validate").clicked() && self.validate() {
-                    self.success_message = Some("✓ Configuration is valid".to_string());
+                if ui.button(tr("texture-validate")).clicked() && self.validate() {
+                    self.success_message = Some(tr("texture-config-valid"));
                 }
 
-                if ui.button("✨ Create Texture").clicked() && self.validate() {
-                    self.success_message = Some(
-                        "✓ Configuration is valid. In a full implementation, the texture would be created here."
-                            .to_string(),
-                    );
+                if ui.button(tr("texture-create")).clicked() && self.validate() {
+                    self.success_message = Some(tr("texture-valid"));
                 }
 
-                if ui.button("🔄 Reset").clicked() {
+                if ui.button(tr("texture-reset")).clicked() {
                     *self = Self::new();
                 }
             });
@@ -616,7 +1313,10 @@ impl TexturePanel {
 
             // Display validation errors or success messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("texture-error", &[("error", error)]),
+                );
             }
 
             if let Some(success) = &self.success_message {
@@ -627,31 +1327,46 @@ impl TexturePanel {
 
             // Current Configuration Summary
             ui.group(|ui| {
-                ui.heading("Configuration Summary");
+                ui.heading(tr("texture-summary-heading"));
                 ui.add_space(5.0);
 
+                let label = if self.label_input.is_empty() {
+                    tr("texture-summary-none")
+                } else {
+                    self.label_input.clone()
+                };
+                ui.monospace(tr_args("texture-summary-label", &[("label", &label)]));
                 ui.monospace(format!(
-                    "Label: {}",
-                    if self.label_input.is_empty() {
-                        "<none>"
-                    } else {
-                        &self.label_input
-                    }
+                    "{} {:?}",
+                    tr("texture-summary-dimension"),
+                    self.selected_dimension
                 ));
-                ui.monospace(format!("Dimension: {:?}", self.selected_dimension));
                 ui.monospace(format!(
-                    "Size: {}x{}x{}",
+                    "{} {}x{}x{}",
+                    tr("texture-summary-size"),
                     self.width_input, self.height_input, self.depth_input
                 ));
-                ui.monospace(format!("Format: {:?}", self.selected_format));
-                ui.monospace(format!("Mip Levels: {}", self.mip_levels_input));
-                ui.monospace(format!("Sample Count: {}", self.sample_count_input));
+                ui.monospace(format!(
+                    "{} {:?}",
+                    tr("texture-summary-format"),
+                    self.selected_format
+                ));
+                ui.monospace(format!(
+                    "{} {}",
+                    tr("texture-summary-mip-levels"),
+                    self.mip_levels_input
+                ));
+                ui.monospace(format!(
+                    "{} {}",
+                    tr("texture-summary-sample-count"),
+                    self.sample_count_input
+                ));
 
                 ui.add_space(5.0);
-                ui.label("Usage flags:");
+                ui.label(tr("texture-summary-usage"));
                 let usage = self.build_usage_flags();
                 if usage.is_empty() {
-                    ui.monospace("  (none)");
+                    ui.monospace(format!("  {}", tr("texture-summary-usage-none")));
                 } else {
                     if usage.contains(TextureUsages::COPY_SRC) {
                         ui.monospace("  • COPY_SRC");
@@ -681,43 +1396,47 @@ impl TexturePanel {
         device: Option<&wgpu::Device>,
         queue: Option<&wgpu::Queue>,
     ) {
+        self.poll_loading();
+        self.poll_bake(device, queue);
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading("🖼️ Texture Configuration");
-            ui.label("Configure and create GPU textures with custom parameters.");
+            ui.heading(tr("texture-heading"));
+            ui.label(tr("texture-intro"));
             ui.add_space(10.0);
 
 
             // Texture Properties
             ui.group(|ui| {
-                ui.heading("Texture Properties");
+                ui.heading(tr("texture-properties"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("texture_properties")
                     .num_columns(2)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Label:")
-                            .webgpu_tooltip("Optional label for debugging and identification", Some("#dom-gpuobjectbase-label"));
+                        ui.label(tr("texture-label")).webgpu_tooltip(
+                            &tr("texture-label-tooltip"),
+                            Some("#dom-gpuobjectbase-label"),
+                        );
                         ui.text_edit_singleline(&mut self.label_input);
                         ui.end_row();
 
-                        property::TEXTURE_WIDTH.apply(ui.label("Width:"));
+                        property::TEXTURE_WIDTH.apply(ui.label(tr("texture-width")));
                         ui.text_edit_singleline(&mut self.width_input);
                         ui.end_row();
 
-                        property::TEXTURE_HEIGHT.apply(ui.label("Height:"));
+                        property::TEXTURE_HEIGHT.apply(ui.label(tr("texture-height")));
                         ui.text_edit_singleline(&mut self.height_input);
                         ui.end_row();
 
-                        property::TEXTURE_DEPTH.apply(ui.label("Depth/Array Layers:"));
+                        property::TEXTURE_DEPTH.apply(ui.label(tr("texture-depth")));
                         ui.text_edit_singleline(&mut self.depth_input);
                         ui.end_row();
 
-                        property::TEXTURE_MIP_LEVELS.apply(ui.label("Mip Levels:"));
+                        property::TEXTURE_MIP_LEVELS.apply(ui.label(tr("texture-mip-levels")));
                         ui.text_edit_singleline(&mut self.mip_levels_input);
                         ui.end_row();
 
-                        property::TEXTURE_SAMPLE_COUNT.apply(ui.label("Sample Count:"));
+                        property::TEXTURE_SAMPLE_COUNT.apply(ui.label(tr("texture-sample-count")));
                         ui.text_edit_singleline(&mut self.sample_count_input);
                         ui.end_row();
                     });
@@ -727,7 +1446,7 @@ impl TexturePanel {
 
             // Texture Dimension
             ui.group(|ui| {
-                ui.heading("Texture Dimension");
+                ui.heading(tr("texture-dimension-heading"));
                 ui.add_space(5.0);
 
                 ui.horizontal(|ui| {
@@ -741,13 +1460,13 @@ impl TexturePanel {
 
             // Texture Format
             ui.group(|ui| {
-                ui.heading("Texture Format");
+                ui.heading(tr("texture-format-heading"));
                 ui.add_space(5.0);
 
-                egui::ComboBox::from_label("Format")
+                egui::ComboBox::from_label(tr("texture-format"))
                     .selected_text(format!("{:?}", self.selected_format))
                     .show_ui(ui, |ui| {
-                        ui.label("Color Formats");
+                        ui.label(tr("texture-format-color"));
                         ui.separator();
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Rgba8Unorm);
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Rgba8UnormSrgb);
@@ -776,7 +1495,7 @@ impl TexturePanel {
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Rgba32Sint);
 
                         ui.add_space(5.0);
-                        ui.label("Depth/Stencil Formats");
+                        ui.label(tr("texture-format-depth-stencil"));
                         ui.separator();
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Depth32Float);
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Depth24Plus);
@@ -784,7 +1503,7 @@ impl TexturePanel {
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Stencil8);
 
                         ui.add_space(5.0);
-                        ui.label("Compressed Formats (BC)");
+                        ui.label(tr("texture-format-compressed"));
                         ui.separator();
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Bc1RgbaUnorm);
                         Self::format_option(ui, &mut self.selected_format, TextureFormat::Bc1RgbaUnormSrgb);
@@ -807,8 +1526,8 @@ impl TexturePanel {
 
             // Usage Flags
             ui.group(|ui| {
-                ui.heading("Usage Flags");
-                ui.label("Select how the texture will be used (multiple flags can be selected):");
+                ui.heading(tr("texture-usage-heading"));
+                ui.label(tr("texture-usage-intro"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("usage_flags")
@@ -853,17 +1572,17 @@ impl TexturePanel {
 
             // File Loading Section
             ui.group(|ui| {
-                ui.heading("📁 Load Texture from File");
-                ui.label("Load image files (PNG, JPEG) to create textures.");
+                ui.heading(tr("texture-file-heading"));
+                ui.label(tr("texture-file-intro"));
                 ui.add_space(5.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("📂 Load Image...").clicked() {
-                        self.file_load_message = Some("Drag and drop an image file onto the browser window to load it.".to_string());
+                    if ui.button(tr("texture-file-load")).clicked() {
+                        self.file_load_message = Some(tr("texture-file-drop-browser"));
                     }
 
                     if self.loaded_texture_data.is_some()
-                        && ui.button("🗑️ Clear Loaded Image").clicked()
+                        && ui.button(tr("texture-file-clear")).clicked()
                     {
                         self.clear_loaded_texture();
                     }
@@ -876,32 +1595,50 @@ impl TexturePanel {
                     ui.colored_label(egui::Color32::GREEN, msg);
                 }
 
+                self.loading_ui(ui);
+
                 if let Some((width, height)) = self.loaded_texture_dimensions {
-                    ui.label(format!("📐 Loaded image: {} x {} pixels", width, height));
-                    ui.label("Image dimensions have been applied to Width and Height fields.");
+                    ui.label(tr_args(
+                        "texture-file-loaded-size",
+                        &[("width", &width), ("height", &height)],
+                    ));
+                    ui.label(tr("texture-file-size-applied"));
                 }
+                self.dpi_ui(ui);
+                self.svg_ui(ui);
 
                 ui.add_space(5.0);
-                ui.label("💡 Tip: Drag and drop image files onto the application window to load them.");
+                ui.label(tr("texture-file-tip-web"));
             });
 
             ui.add_space(15.0);
 
+            self.procedural_ui(ui, device, queue);
+
+            ui.add_space(15.0);
+
             // Preview Section
             if self.show_preview && (self.loaded_texture_data.is_some() || self.width_input.parse::<u32>().is_ok()) {
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.heading("🎨 Texture Preview");
-                        if ui.small_button("✕").on_hover_text("Hide preview").clicked() {
+                        ui.heading(tr("texture-preview-heading"));
+                        if ui
+                            .small_button("✕")
+                            .on_hover_text(tr("texture-preview-hide"))
+                            .clicked()
+                        {
                             self.show_preview = false;
                         }
                     });
                     ui.add_space(5.0);
 
                     if self.loaded_texture_data.is_some() {
-                        ui.label("Preview shows the loaded image texture:");
+                        ui.label(tr("texture-preview-image"));
                     } else {
-                        ui.label("Preview shows a procedural checkerboard texture:");
+                        ui.label(tr_args(
+                            "texture-preview-procedural",
+                            &[("pattern", &self.procedural.generator.name())],
+                        ));
                     }
 
                     ui.add_space(5.0);
@@ -910,7 +1647,7 @@ impl TexturePanel {
                     if let Some(device) = device {
                         if self.preview_state.is_none() {
                             let mut preview = TexturePreviewState::new();
-                            preview.initialize(device);
+                            self.gpu_errors.scoped(device, PREVIEW_TEXTURE, || preview.initialize(device));
                             self.preview_state = Some(preview);
                         }
                     }
@@ -919,47 +1656,44 @@ impl TexturePanel {
                     if let (Some(preview), Some(device), Some(queue)) =
                         (&mut self.preview_state, device, queue)
                     {
-                        if let Some(loaded_data) = &self.loaded_texture_data {
-                            // Display loaded image
-                            if let Some((width, height)) = self.loaded_texture_dimensions {
-                                // Convert image data to RGBA if needed
-                                if let Ok(img) = image::load_from_memory(loaded_data) {
-                                    let rgba = img.to_rgba8();
-                                    let rgba_data = rgba.as_raw();
-
-                                    if !preview.has_texture() || self.file_load_message.is_some() {
-                                        preview.update_from_image_data(device, queue, rgba_data, width, height);
-                                        // Clear the file load message after updating preview
-                                        self.file_load_message = None;
-                                    }
-                                }
+                        if let Some(image) = &self.decoded_image {
+                            // Display loaded image, written a few rows per frame
+                            if !preview.has_texture() || self.file_load_message.is_some() {
+                                self.gpu_errors.scoped(device, PREVIEW_TEXTURE, || {
+                                    preview.begin_image_upload(device, image.clone())
+                                });
+                                // Clear the file load message after updating preview
+                                self.file_load_message = None;
                             }
-                        } else if self.width_input.parse::<u32>().is_ok() && self.height_input.parse::<u32>().is_ok() {
-                            // Generate procedural texture
-                            let width = self.width_input.parse::<u32>().unwrap_or(256);
-                            let height = self.height_input.parse::<u32>().unwrap_or(256);
-
-                            if !preview.has_texture() {
-                                preview.generate_procedural_texture(device, queue, width, height);
+                            self.upload_progress = preview.continue_upload(queue, DEFAULT_UPLOAD_BUDGET);
+                        } else if let (Ok(width), Ok(height)) =
+                            (self.width_input.parse::<u32>(), self.height_input.parse::<u32>())
+                        {
+                            // Generate the procedural pattern again when its settings change
+                            let (width, height) = procedural_texture::preview_size(width, height, PROCEDURAL_PREVIEW_SIZE);
+                            let source = Some((self.procedural.clone(), width, height));
+                            if !preview.has_texture() || self.procedural_source != source {
+                                self.gpu_errors.scoped(device, PREVIEW_TEXTURE, || {
+                                    preview.generate_procedural_texture(device, queue, &self.procedural, width, height)
+                                });
+                                self.procedural_source = source;
                             }
                         }
+                        self.gpu_errors.ui(ui, PREVIEW_TEXTURE);
 
                         // Render preview
                         preview.render(device, queue);
 
                         // Display the preview texture - not available on WASM
                     } else if device.is_none() {
-                        ui.colored_label(
-                            egui::Color32::YELLOW,
-                            "⚠ Preview requires GPU device to be initialized"
-                        );
+                        ui.colored_label(egui::Color32::YELLOW, tr("texture-preview-needs-device"));
                     }
                 });
 
                 ui.add_space(15.0);
             } else if self.loaded_texture_data.is_some() || self.width_input.parse::<u32>().is_ok() {
                 // Show button to enable preview
-                if ui.button("🎨 Show Texture Preview").clicked() {
+                if ui.button(tr("texture-preview-show")).clicked() {
                     self.show_preview = true;
                 }
             }
@@ -968,18 +1702,15 @@ impl TexturePanel {
 
             // Validation and Creation
             ui.horizontal(|ui| {
-                if ui.button("🔍 Validate").clicked() && self.validate() {
-                    self.success_message = Some("✓ Configuration is valid".to_string());
+                if ui.button(tr("texture-validate")).clicked() && self.validate() {
+                    self.success_message = Some(tr("texture-config-valid"));
                 }
 
-                if ui.button("✨ Create Texture").clicked() && self.validate() {
-                    self.success_message = Some(
-                        "✓ Configuration is valid. In a full implementation, the texture would be created here."
-                            .to_string(),
-                    );
+                if ui.button(tr("texture-create")).clicked() && self.validate() {
+                    self.success_message = Some(tr("texture-valid"));
                 }
 
-                if ui.button("🔄 Reset").clicked() {
+                if ui.button(tr("texture-reset")).clicked() {
                     *self = Self::new();
                 }
             });
@@ -988,7 +1719,10 @@ impl TexturePanel {
 
             // Display validation errors or success messages
             if let Some(error) = &self.validation_error {
-                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args("texture-error", &[("error", error)]),
+                );
             }
 
             if let Some(success) = &self.success_message {
@@ -999,31 +1733,46 @@ impl TexturePanel {
 
             // Current Configuration Summary
             ui.group(|ui| {
-                ui.heading("Configuration Summary");
+                ui.heading(tr("texture-summary-heading"));
                 ui.add_space(5.0);
 
+                let label = if self.label_input.is_empty() {
+                    tr("texture-summary-none")
+                } else {
+                    self.label_input.clone()
+                };
+                ui.monospace(tr_args("texture-summary-label", &[("label", &label)]));
                 ui.monospace(format!(
-                    "Label: {}",
-                    if self.label_input.is_empty() {
-                        "<none>"
-                    } else {
-                        &self.label_input
-                    }
+                    "{} {:?}",
+                    tr("texture-summary-dimension"),
+                    self.selected_dimension
                 ));
-                ui.monospace(format!("Dimension: {:?}", self.selected_dimension));
                 ui.monospace(format!(
-                    "Size: {}x{}x{}",
+                    "{} {}x{}x{}",
+                    tr("texture-summary-size"),
                     self.width_input, self.height_input, self.depth_input
                 ));
-                ui.monospace(format!("Format: {:?}", self.selected_format));
-                ui.monospace(format!("Mip Levels: {}", self.mip_levels_input));
-                ui.monospace(format!("Sample Count: {}", self.sample_count_input));
+                ui.monospace(format!(
+                    "{} {:?}",
+                    tr("texture-summary-format"),
+                    self.selected_format
+                ));
+                ui.monospace(format!(
+                    "{} {}",
+                    tr("texture-summary-mip-levels"),
+                    self.mip_levels_input
+                ));
+                ui.monospace(format!(
+                    "{} {}",
+                    tr("texture-summary-sample-count"),
+                    self.sample_count_input
+                ));
 
                 ui.add_space(5.0);
-                ui.label("Usage flags:");
+                ui.label(tr("texture-summary-usage"));
                 let usage = self.build_usage_flags();
                 if usage.is_empty() {
-                    ui.monospace("  (none)");
+                    ui.monospace(format!("  {}", tr("texture-summary-usage-none")));
                 } else {
                     if usage.contains(TextureUsages::COPY_SRC) {
                         ui.monospace("  • COPY_SRC");
@@ -1075,14 +1824,14 @@ impl TexturePanel {
             usage_texture_binding: self.usage_texture_binding,
             usage_storage_binding: self.usage_storage_binding,
             usage_render_attachment: self.usage_render_attachment,
+            image_data: self
+                .loaded_texture_data
+                .as_ref()
+                .map(|bytes| BASE64_STANDARD.encode(bytes)),
         }
     }
 
     /// Import state from a serializable format
-    ///
-    /// Note: Format and dimension enum values are stored as strings but are not parsed back
-    /// to avoid complexity. The panel will retain default values for these fields.
-    /// Future enhancement could add enum parsing support.
     pub fn import_state(&mut self, state: &crate::state::TexturePanelState) {
         self.label_input = state.label.clone();
         self.width_input = state.width.clone();
@@ -1096,16 +1845,56 @@ impl TexturePanel {
         self.usage_storage_binding = state.usage_storage_binding;
         self.usage_render_attachment = state.usage_render_attachment;
 
-        // NOTE: Format and dimension are not parsed from saved state strings.
-        // Current behavior: These fields reset to default values when loading state.
-        // To implement parsing:
-        // 1. Add parse_texture_format(&str) -> Option<TextureFormat> helper
-        // 2. Add parse_texture_dimension(&str) -> Option<TextureDimension> helper
-        // 3. Use these to restore format/dimension from state.format_str/dimension_str
-        // Alternative: Store enum discriminants as integers in state instead of strings
+        if let Some(format) = parse_variant(&state.format, &SELECTABLE_FORMATS) {
+            self.selected_format = format;
+        }
+        if let Some(dimension) = parse_variant(
+            &state.dimension,
+            &[
+                TextureDimension::D1,
+                TextureDimension::D2,
+                TextureDimension::D3,
+            ],
+        ) {
+            self.selected_dimension = dimension;
+        }
 
         self.validation_error = None;
         self.success_message = None;
+
+        self.clear_loaded_texture();
+        if let Some(image_data) = &state.image_data {
+            match BASE64_STANDARD.decode(image_data) {
+                // SVGs are drawn again at the saved size
+                Ok(bytes) if svg::is_svg(&bytes) => {
+                    let width = self.width_input.parse::<u32>().unwrap_or(0);
+                    let height = self.height_input.parse::<u32>().unwrap_or(0);
+                    if width > 0 && height > 0 {
+                        self.start_svg_rasterize(bytes, width, height);
+                    } else {
+                        self.load_from_bytes(bytes);
+                    }
+                }
+                Ok(bytes) => self.load_from_bytes(bytes),
+                Err(e) => {
+                    self.validation_error =
+                        Some(tr_args("texture-file-saved-failed", &[("error", &e)]))
+                }
+            }
+        }
+    }
+}
+
+impl ResourceRecreate for TexturePanel {
+    fn recreate_resources(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue) {
+        // The preview is rebuilt from the loaded data on the next frame
+        self.preview_state = None;
+        self.mip_debug = None;
+        self.mip_debug_source = None;
+        self.procedural_source = None;
+        // Textures of the lost device can't be bound; bake again
+        self.baked = None;
+        self.gpu_errors.clear_all();
     }
 }
 
@@ -1313,6 +2102,7 @@ mod tests {
         ];
 
         panel.load_from_bytes(png_data);
+        panel.finish_loading();
 
         // Should have loaded successfully
         assert!(panel.loaded_texture_data.is_some());
@@ -1322,6 +2112,131 @@ mod tests {
         assert_eq!(panel.height_input, "1");
         assert!(panel.file_load_message.is_some());
         assert!(panel.validation_error.is_none());
+        assert_eq!(
+            panel.decoded_image.as_ref().map(|image| image.rgba.len()),
+            Some(4)
+        );
+        assert!(!panel.is_loading());
+    }
+
+    #[test]
+    fn test_state_roundtrip_keeps_loaded_image() {
+        let png_data = vec![
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48,
+            0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00,
+            0x00, 0x90, 0x77, 0x53, 0xDE, 0x00, 0x00, 0x00, 0x0C, 0x49, 0x44, 0x41, 0x54, 0x08,
+            0xD7, 0x63, 0xF8, 0xFF, 0xFF, 0x3F, 0x00, 0x05, 0xFE, 0x02, 0xFE, 0xDC, 0xCC, 0x59,
+            0xE7, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+        ];
+        let mut panel = TexturePanel::new();
+        panel.load_from_bytes(png_data.clone());
+        panel.finish_loading();
+
+        let state = panel.export_state();
+        assert!(state.image_data.is_some());
+
+        let mut restored = TexturePanel::new();
+        restored.import_state(&state);
+        restored.finish_loading();
+        assert_eq!(restored.loaded_texture_data, Some(png_data));
+        assert_eq!(restored.loaded_texture_dimensions, Some((1, 1)));
+
+        // A state without an image clears the loaded one
+        restored.import_state(&TexturePanel::new().export_state());
+        assert!(restored.loaded_texture_data.is_none());
+    }
+
+    #[test]
+    fn test_paste_image_keeps_resolution() {
+        let mut panel = TexturePanel::new();
+        let image = DecodedImage {
+            width: 4,
+            height: 2,
+            rgba: vec![200; 32],
+        };
+        assert_eq!(panel.paste_image(image.clone(), Some(192.0)), Ok((4, 2)));
+        assert_eq!(panel.width_input, "4");
+        assert_eq!(panel.height_input, "2");
+        assert_eq!(panel.get_loaded_texture_dpi(), Some(192.0));
+        assert_eq!(panel.decoded_image.as_ref(), Some(&image));
+        assert!(panel.file_load_message.is_some());
+
+        // The stored PNG decodes back to the image, resolution included
+        let mut restored = TexturePanel::new();
+        restored.import_state(&panel.export_state());
+        restored.finish_loading();
+        assert_eq!(restored.decoded_image, Some(image));
+        assert_eq!(restored.get_loaded_texture_dpi(), Some(192.0));
+    }
+
+    #[test]
+    fn test_svg_rerasterizes_at_chosen_size() {
+        let svg_data = br#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+            <rect width="20" height="10" fill="green"/></svg>"#
+            .to_vec();
+        let mut panel = TexturePanel::new();
+        panel.load_from_bytes(svg_data.clone());
+        panel.finish_loading();
+        assert_eq!(panel.loaded_texture_dimensions, Some((20, 10)));
+        assert_eq!(panel.get_svg_raster_size(), Some((20, 10)));
+
+        panel.rasterize_svg(80, 40);
+        panel.finish_loading();
+        assert_eq!(panel.loaded_texture_dimensions, Some((80, 40)));
+        assert_eq!(panel.width_input, "80");
+        // The source is kept, not the pixels
+        assert_eq!(panel.loaded_texture_data, Some(svg_data));
+
+        let mut restored = TexturePanel::new();
+        restored.import_state(&panel.export_state());
+        restored.finish_loading();
+        assert_eq!(restored.loaded_texture_dimensions, Some((80, 40)));
+
+        // Other images have nothing to rasterize
+        panel
+            .paste_image(
+                DecodedImage {
+                    width: 1,
+                    height: 1,
+                    rgba: vec![0; 4],
+                },
+                None,
+            )
+            .unwrap();
+        assert_eq!(panel.get_svg_raster_size(), None);
+        panel.rasterize_svg(10, 10);
+        assert!(!panel.is_loading());
+    }
+
+    #[test]
+    fn test_bake_loads_generated_texture() {
+        let mut panel = TexturePanel::new();
+        panel.width_input = "32".to_string();
+        panel.height_input = "16".to_string();
+        panel.bake();
+        assert!(panel.is_baking());
+        // Without a device only the image is kept
+        panel.finish_bake(None, None);
+        assert!(!panel.is_baking());
+        assert_eq!(panel.loaded_texture_dimensions, Some((32, 16)));
+        assert!(panel.loaded_from_generator);
+        assert!(panel.baked_texture().is_none());
+        let pattern = panel.decoded_image.clone().unwrap();
+        assert_eq!(
+            pattern,
+            procedural_texture::generate(&panel.procedural, 32, 16)
+        );
+
+        panel.normal_map_from_image(None, None);
+        let normal_map = panel.decoded_image.clone().unwrap();
+        assert_eq!((normal_map.width, normal_map.height), (32, 16));
+        assert_ne!(normal_map, pattern);
+        assert!(!panel.loaded_from_generator);
+
+        panel.width_input = "0".to_string();
+        panel.bake();
+        assert!(!panel.is_baking());
+        assert!(panel.validation_error.is_some());
     }
 
     #[test]
@@ -1330,6 +2245,7 @@ mod tests {
         let invalid_data = vec![0u8; 100];
 
         panel.load_from_bytes(invalid_data);
+        panel.finish_loading();
 
         // Should have failed to load
         assert!(panel.loaded_texture_data.is_none());
@@ -1351,6 +2267,7 @@ mod tests {
             0xE7, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
         ];
         panel.load_from_bytes(png_data);
+        panel.finish_loading();
 
         // Verify it's loaded
         assert!(panel.loaded_texture_data.is_some());
@@ -1363,4 +2280,17 @@ mod tests {
         assert!(panel.loaded_texture_dimensions.is_none());
         assert!(panel.file_load_message.is_none());
     }
+
+    #[test]
+    fn test_clear_cancels_pending_load() {
+        let mut panel = TexturePanel::new();
+        panel.load_from_bytes(vec![0u8; 100]);
+        assert!(panel.is_loading());
+
+        panel.clear_loaded_texture();
+        assert!(!panel.is_loading());
+        panel.finish_loading();
+        assert!(panel.loaded_texture_data.is_none());
+        assert!(panel.validation_error.is_none());
+    }
 }
@@ -2,8 +2,10 @@
 ///
 /// Provides live visualization of texture configurations:
 /// - Loaded images: Shows the image as a textured quad
-/// - Procedural textures: Generates and displays procedural patterns
+/// - Procedural textures: Shows the pattern of the procedural generator
 use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::asset_loader::{DecodedImage, TextureUpload};
+use crate::procedural_texture::{self, ProceduralSettings};
 use wgpu::util::DeviceExt;
 
 /// Vertex structure for texture quad rendering
@@ -26,6 +28,8 @@ pub struct TexturePreviewState {
     preview_texture: Option<wgpu::Texture>,
     /// Preview texture view
     preview_texture_view: Option<wgpu::TextureView>,
+    /// Image still being written into the preview texture
+    pending_upload: Option<TextureUpload>,
     /// Sampler for texture preview
     sampler: Option<wgpu::Sampler>,
     /// Bind group for texture preview
@@ -52,6 +56,7 @@ impl TexturePreviewState {
             preview_index_buffer: None,
             preview_texture: None,
             preview_texture_view: None,
+            pending_upload: None,
             sampler: None,
             texture_bind_group: None,
             bind_group_layout: None,
@@ -277,7 +282,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview_mask: None,
-            cache: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
         });
 
         self.bind_group_layout = Some(bind_group_layout);
@@ -293,24 +298,9 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
         width: u32,
         height: u32,
     ) {
+        crate::cpu_scope!("Upload texture preview");
         let tracker = ApiCoverageTracker::global();
-
-        // Create preview texture from image data
-        tracker.record(ApiCategory::Texture, "create_texture");
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Texture Preview Source"),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
+        let texture = Self::create_source_texture(device, width, height);
 
         // Write image data to texture
         tracker.record(ApiCategory::Queue, "write_texture");
@@ -334,7 +324,53 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
             },
         );
 
-        tracker.record(ApiCategory::Texture, "create_view");
+        self.pending_upload = None;
+        self.set_source_texture(device, texture);
+    }
+
+    /// Show a decoded image, written into the preview a few rows per frame
+    /// by [`continue_upload`](Self::continue_upload)
+    pub fn begin_image_upload(&mut self, device: &wgpu::Device, image: DecodedImage) {
+        let texture = Self::create_source_texture(device, image.width, image.height);
+        self.set_source_texture(device, texture.clone());
+        self.pending_upload = Some(TextureUpload::new(texture, image));
+    }
+
+    /// Write the next rows of an image from
+    /// [`begin_image_upload`](Self::begin_image_upload), up to `budget`
+    /// bytes
+    ///
+    /// Returns the fraction written while the upload is unfinished.
+    pub fn continue_upload(&mut self, queue: &wgpu::Queue, budget: u64) -> Option<f32> {
+        let upload = self.pending_upload.as_mut()?;
+        crate::cpu_scope!("Upload texture preview");
+        if upload.step(queue, budget) {
+            self.pending_upload = None;
+            return None;
+        }
+        Some(upload.progress())
+    }
+
+    fn create_source_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu::Texture {
+        ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Texture Preview Source"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        })
+    }
+
+    fn set_source_texture(&mut self, device: &wgpu::Device, texture: wgpu::Texture) {
+        ApiCoverageTracker::global().record(ApiCategory::Texture, "create_view");
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
         self.preview_texture = Some(texture);
@@ -344,37 +380,17 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
         self.update_bind_group(device);
     }
 
-    /// Generate and update procedural texture
+    /// Show the procedural texture of `settings`
     pub fn generate_procedural_texture(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
+        settings: &ProceduralSettings,
         width: u32,
         height: u32,
     ) {
-        // Generate a simple procedural checkerboard pattern
-        let mut data = vec![0u8; (width * height * 4) as usize];
-
-        for y in 0..height {
-            for x in 0..width {
-                let idx = ((y * width + x) * 4) as usize;
-                let checker = ((x / 32) + (y / 32)) % 2;
-
-                if checker == 0 {
-                    data[idx] = 200; // R
-                    data[idx + 1] = 200; // G
-                    data[idx + 2] = 200; // B
-                    data[idx + 3] = 255; // A
-                } else {
-                    data[idx] = 100; // R
-                    data[idx + 1] = 100; // G
-                    data[idx + 2] = 100; // B
-                    data[idx + 3] = 255; // A
-                }
-            }
-        }
-
-        self.update_from_image_data(device, queue, &data, width, height);
+        let image = procedural_texture::generate(settings, width, height);
+        self.update_from_image_data(device, queue, &image.rgba, image.width, image.height);
     }
 
     /// Update bind group with current texture
@@ -408,6 +424,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
 
     /// Render texture preview
     pub fn render(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Option<&wgpu::TextureView> {
+        crate::cpu_scope!("Render texture preview");
         let tracker = ApiCoverageTracker::global();
 
         // Only render if we have a texture to preview
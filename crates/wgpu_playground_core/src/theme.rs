@@ -0,0 +1,317 @@
+//! Colors of the user interface and of rendering previews
+//!
+//! A [`ThemePalette`] holds everything a theme controls: the egui visuals,
+//! the clear color new previews start with and the backdrop painted behind
+//! previews so that transparency shows. The built-in dark and light themes
+//! have fixed palettes; the custom theme's palette is edited in the Settings
+//! tab and saved with the rest of the settings.
+//!
+//! The palette of the active theme is kept globally (see [`current`]) so
+//! panels can paint backdrops without it being passed down to them.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{OnceLock, RwLock};
+
+use crate::i18n::tr;
+use crate::state::Theme;
+
+/// Pattern painted behind previews so that transparent pixels show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BackdropPattern {
+    /// The first backdrop color
+    Solid,
+    /// Squares alternating between both backdrop colors
+    #[default]
+    Checkerboard,
+    /// Lines of the second color on the first
+    Grid,
+    /// Diagonal bands alternating between both colors
+    Stripes,
+}
+
+impl BackdropPattern {
+    pub const ALL: [BackdropPattern; 4] = [
+        BackdropPattern::Solid,
+        BackdropPattern::Checkerboard,
+        BackdropPattern::Grid,
+        BackdropPattern::Stripes,
+    ];
+
+    /// Display name in the current language
+    pub fn name(&self) -> String {
+        tr(match self {
+            BackdropPattern::Solid => "settings-theme-backdrop-solid",
+            BackdropPattern::Checkerboard => "settings-theme-backdrop-checkerboard",
+            BackdropPattern::Grid => "settings-theme-backdrop-grid",
+            BackdropPattern::Stripes => "settings-theme-backdrop-stripes",
+        })
+    }
+
+    /// Paint the pattern over `rect` in squares of `cell` points
+    pub fn paint(
+        &self,
+        painter: &egui::Painter,
+        rect: egui::Rect,
+        colors: [egui::Color32; 2],
+        cell: f32,
+    ) {
+        let cell = cell.max(2.0);
+        painter.rect_filled(rect, 0.0, colors[0]);
+        match self {
+            BackdropPattern::Solid => {}
+            BackdropPattern::Checkerboard => {
+                let columns = (rect.width() / cell).ceil() as u32;
+                let rows = (rect.height() / cell).ceil() as u32;
+                for row in 0..rows {
+                    for column in (row % 2..columns).step_by(2) {
+                        let min = rect.min + egui::vec2(column as f32, row as f32) * cell;
+                        let square =
+                            egui::Rect::from_min_size(min, egui::vec2(cell, cell)).intersect(rect);
+                        painter.rect_filled(square, 0.0, colors[1]);
+                    }
+                }
+            }
+            BackdropPattern::Grid => {
+                let stroke = egui::Stroke::new(1.0, colors[1]);
+                let mut x = rect.left();
+                while x <= rect.right() {
+                    painter.vline(x, rect.y_range(), stroke);
+                    x += cell;
+                }
+                let mut y = rect.top();
+                while y <= rect.bottom() {
+                    painter.hline(rect.x_range(), y, stroke);
+                    y += cell;
+                }
+            }
+            BackdropPattern::Stripes => {
+                // Bands at 45°, clipped to the rect
+                let painter = painter.with_clip_rect(rect.intersect(painter.clip_rect()));
+                let stroke = egui::Stroke::new(cell * std::f32::consts::FRAC_1_SQRT_2, colors[1]);
+                let mut offset = 0.0;
+                while offset < rect.width() + rect.height() {
+                    let start = egui::pos2(rect.left() + offset, rect.top());
+                    let end = start + egui::vec2(-rect.height(), rect.height());
+                    painter.line_segment([start, end], stroke);
+                    offset += cell * 2.0;
+                }
+            }
+        }
+    }
+}
+
+/// Everything a theme controls
+///
+/// UI colors are sRGB bytes like egui's; the preview clear color is linear
+/// RGBA like the render passes that use it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemePalette {
+    /// Start from egui's dark visuals rather than its light ones
+    pub dark: bool,
+    /// Background of side and central panels
+    pub panel_fill: [u8; 3],
+    /// Background of windows and popups
+    pub window_fill: [u8; 3],
+    /// Selections and links
+    pub accent: [u8; 3],
+    /// Color of all text, egui's text colors when `None`
+    pub text: Option<[u8; 3]>,
+    /// Clear color of new rendering previews
+    pub preview_clear: [f32; 4],
+    /// Pattern painted behind previews
+    pub backdrop: BackdropPattern,
+    /// The two colors of the backdrop pattern
+    pub backdrop_colors: [[u8; 3]; 2],
+    /// Size of the backdrop's squares in points
+    pub backdrop_cell: f32,
+}
+
+impl Default for ThemePalette {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl ThemePalette {
+    /// Palette of the built-in dark theme
+    pub fn dark() -> Self {
+        Self::from_visuals(
+            &egui::Visuals::dark(),
+            [0.05, 0.05, 0.1, 1.0],
+            [[90, 90, 90], [150, 150, 150]],
+        )
+    }
+
+    /// Palette of the built-in light theme
+    pub fn light() -> Self {
+        Self::from_visuals(
+            &egui::Visuals::light(),
+            [0.8, 0.82, 0.88, 1.0],
+            [[204, 204, 204], [255, 255, 255]],
+        )
+    }
+
+    /// Palette matching egui's `visuals` exactly
+    fn from_visuals(
+        visuals: &egui::Visuals,
+        preview_clear: [f32; 4],
+        backdrop_colors: [[u8; 3]; 2],
+    ) -> Self {
+        let rgb = |color: egui::Color32| [color.r(), color.g(), color.b()];
+        Self {
+            dark: visuals.dark_mode,
+            panel_fill: rgb(visuals.panel_fill),
+            window_fill: rgb(visuals.window_fill),
+            accent: rgb(visuals.selection.bg_fill),
+            text: None,
+            preview_clear,
+            backdrop: BackdropPattern::Checkerboard,
+            backdrop_colors,
+            backdrop_cell: 8.0,
+        }
+    }
+
+    /// Palette of `theme`, with `custom` used for [`Theme::Custom`]
+    pub fn for_theme(theme: Theme, custom: &ThemePalette) -> Self {
+        match theme {
+            Theme::Dark => Self::dark(),
+            Theme::Light => Self::light(),
+            Theme::Custom => custom.clone(),
+        }
+    }
+
+    /// egui visuals of the palette
+    pub fn visuals(&self) -> egui::Visuals {
+        let mut visuals = if self.dark {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        let color = |[r, g, b]: [u8; 3]| egui::Color32::from_rgb(r, g, b);
+        visuals.panel_fill = color(self.panel_fill);
+        visuals.window_fill = color(self.window_fill);
+        visuals.selection.bg_fill = color(self.accent);
+        // Links keep egui's blue unless the accent was changed
+        if self.accent != Self::base(self.dark).accent {
+            visuals.hyperlink_color = color(self.accent);
+        }
+        visuals.override_text_color = self.text.map(color);
+        visuals
+    }
+
+    /// Built-in palette with the same base visuals
+    fn base(dark: bool) -> Self {
+        if dark {
+            Self::dark()
+        } else {
+            Self::light()
+        }
+    }
+
+    /// Colors of the backdrop pattern
+    pub fn backdrop_colors(&self) -> [egui::Color32; 2] {
+        self.backdrop_colors
+            .map(|[r, g, b]| egui::Color32::from_rgb(r, g, b))
+    }
+
+    /// Paint the palette's backdrop over `rect`
+    pub fn paint_backdrop(&self, painter: &egui::Painter, rect: egui::Rect) {
+        self.paint_backdrop_pattern(painter, rect, self.backdrop);
+    }
+
+    /// Paint `pattern` in the palette's backdrop colors over `rect`
+    pub fn paint_backdrop_pattern(
+        &self,
+        painter: &egui::Painter,
+        rect: egui::Rect,
+        pattern: BackdropPattern,
+    ) {
+        pattern.paint(painter, rect, self.backdrop_colors(), self.backdrop_cell);
+    }
+}
+
+fn palette() -> &'static RwLock<ThemePalette> {
+    static PALETTE: OnceLock<RwLock<ThemePalette>> = OnceLock::new();
+    PALETTE.get_or_init(|| RwLock::new(ThemePalette::default()))
+}
+
+/// Palette of the active theme
+pub fn current() -> ThemePalette {
+    match palette().read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Make `palette` the active one and apply its visuals to `ctx`
+pub fn apply(ctx: &egui::Context, new_palette: &ThemePalette) {
+    ctx.set_visuals(new_palette.visuals());
+    match palette().write() {
+        Ok(mut guard) => *guard = new_palette.clone(),
+        Err(poisoned) => *poisoned.into_inner() = new_palette.clone(),
+    }
+}
+
+/// Combo box choosing a backdrop pattern; true if the choice changed
+pub fn backdrop_selector(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    pattern: &mut BackdropPattern,
+) -> bool {
+    let previous = *pattern;
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(pattern.name())
+        .show_ui(ui, |ui| {
+            for option in BackdropPattern::ALL {
+                ui.selectable_value(pattern, option, option.name());
+            }
+        });
+    *pattern != previous
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_palettes_match_egui() {
+        assert_eq!(ThemePalette::dark().visuals(), egui::Visuals::dark());
+        assert_eq!(ThemePalette::light().visuals(), egui::Visuals::light());
+    }
+
+    #[test]
+    fn test_custom_palette_overrides_visuals() {
+        let custom = ThemePalette {
+            panel_fill: [10, 20, 30],
+            accent: [200, 100, 0],
+            text: Some([250, 250, 250]),
+            ..ThemePalette::dark()
+        };
+        let visuals = ThemePalette::for_theme(Theme::Custom, &custom).visuals();
+        assert_eq!(visuals.panel_fill, egui::Color32::from_rgb(10, 20, 30));
+        assert_eq!(
+            visuals.selection.bg_fill,
+            egui::Color32::from_rgb(200, 100, 0)
+        );
+        assert_eq!(
+            visuals.hyperlink_color,
+            egui::Color32::from_rgb(200, 100, 0)
+        );
+        assert_eq!(
+            visuals.override_text_color,
+            Some(egui::Color32::from_rgb(250, 250, 250))
+        );
+        assert_eq!(
+            ThemePalette::for_theme(Theme::Light, &custom),
+            ThemePalette::light()
+        );
+    }
+
+    #[test]
+    fn test_partial_palette_uses_defaults() {
+        let palette: ThemePalette = serde_json::from_str(r#"{"backdrop":"Grid"}"#).unwrap();
+        assert_eq!(palette.backdrop, BackdropPattern::Grid);
+        assert_eq!(palette.panel_fill, ThemePalette::dark().panel_fill);
+    }
+}
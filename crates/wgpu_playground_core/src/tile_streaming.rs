@@ -0,0 +1,1124 @@
+//! Tiled resource streaming: a virtual texture that is loaded on demand
+//!
+//! The ground plane is textured with an 8192x8192 texture and its mip
+//! chain, cut into 128x128 tiles. Only the tiles the camera needs are
+//! generated and copied into a 2048x2048 atlas; a page table in a storage
+//! buffer maps every tile of the virtual texture to its atlas slot.
+//!
+//! The loop closes through the GPU: while shading, a sparse set of pixels
+//! appends the tile they wanted to a request list. The list is copied into
+//! a readback buffer and mapped without stalling, like the
+//! [`crate::debug_counters`] readback, and a frame or two later the CPU
+//! streams the missing tiles into the atlas, evicting the least recently
+//! requested ones when it is full. Until a tile arrives the shader falls
+//! back to the next coarser resident mip, so the image sharpens as tiles
+//! stream in. The whole virtual texture would need about 340 MiB; the
+//! atlas uses 16 MiB and stays within the default WebGPU texture limits.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::Mat4;
+use crate::upload_belt;
+
+/// Width and height of a tile in texels
+pub const TILE_SIZE: u32 = 128;
+
+/// Tiles along each side of the virtual texture at mip 0
+pub const TILES_PER_SIDE: u32 = 64;
+
+/// Mips of the virtual texture; the coarsest is a single tile
+pub const MIP_COUNT: u32 = 7;
+
+/// Tiles in the virtual texture, over all mips
+pub const TILE_COUNT: u32 = mip_offset(MIP_COUNT);
+
+/// Atlas slots along each side of the atlas texture
+pub const ATLAS_TILES_PER_ROW: u32 = 16;
+
+/// Tiles that fit in the atlas at once
+pub const ATLAS_CAPACITY: u32 = ATLAS_TILES_PER_ROW * ATLAS_TILES_PER_ROW;
+
+/// Length of the request list written by the fragment shader
+pub const MAX_REQUESTS: u32 = 1024;
+
+/// One in `FEEDBACK_STRIDE` x `FEEDBACK_STRIDE` pixels reports its tile
+pub const FEEDBACK_STRIDE: u32 = 8;
+
+/// Side length of the ground plane in world units
+pub const WORLD_SIZE: f32 = 64.0;
+
+const ATLAS_SIZE: u32 = ATLAS_TILES_PER_ROW * TILE_SIZE;
+const ATLAS_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+const TILE_BYTES: usize = (TILE_SIZE * TILE_SIZE * 4) as usize;
+const PAGE_TABLE_SIZE: u64 = TILE_COUNT as u64 * 4;
+/// The request count followed by the requested tile indices
+const REQUESTS_SIZE: u64 = (1 + MAX_REQUESTS as u64) * 4;
+
+pub const TILE_STREAMING_SHADER: &str = r#"// Tiled Resource Streaming Example
+// The ground texture is virtual: only the tiles the camera needs live in
+// the atlas. The page table maps a tile index to its atlas slot (slot + 1,
+// 0 = not resident) and the fragment shader reports the tiles it wanted.
+
+const TILE_SIZE: f32 = 128.0;
+const TILES_PER_SIDE: u32 = 64u;
+const MIP_COUNT: u32 = 7u;
+const TILE_COUNT: u32 = 5461u;
+const ATLAS_TILES_PER_ROW: u32 = 16u;
+const MAX_REQUESTS: u32 = 1024u;
+const FEEDBACK_STRIDE: u32 = 8u;
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    world_size: f32,
+    frame: u32,
+    mip_bias: f32,
+    show_tiles: u32,
+}
+
+struct Requests {
+    count: atomic<u32>,
+    ids: array<u32, MAX_REQUESTS>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var atlas: texture_2d<f32>;
+@group(0) @binding(2) var atlas_sampler: sampler;
+@group(0) @binding(3) var<storage, read> page_table: array<u32, TILE_COUNT>;
+// One flag per tile so that every tile is requested at most once a frame
+@group(0) @binding(4) var<storage, read_write> requested: array<atomic<u32>, TILE_COUNT>;
+@group(0) @binding(5) var<storage, read_write> requests: Requests;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// A ground plane of world_size x world_size centered on the origin
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+    );
+    let uv = corners[vertex_index];
+    let xz = (uv - 0.5) * uniforms.world_size;
+
+    var out: VertexOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(xz.x, 0.0, xz.y, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+fn tiles_at(mip: u32) -> u32 {
+    return TILES_PER_SIDE >> mip;
+}
+
+// First index of the tiles of `mip`; mip 0 comes first
+fn mip_offset(mip: u32) -> u32 {
+    var offset = 0u;
+    for (var m = 0u; m < mip; m++) {
+        offset += tiles_at(m) * tiles_at(m);
+    }
+    return offset;
+}
+
+fn tile_index(uv: vec2<f32>, mip: u32) -> u32 {
+    let tiles = tiles_at(mip);
+    let coord = min(vec2<u32>(uv * f32(tiles)), vec2<u32>(tiles - 1u));
+    return mip_offset(mip) + coord.y * tiles + coord.x;
+}
+
+fn request(tile: u32) {
+    if atomicExchange(&requested[tile], 1u) == 0u {
+        let slot = atomicAdd(&requests.count, 1u);
+        if slot < MAX_REQUESTS {
+            requests.ids[slot] = tile;
+        }
+    }
+}
+
+fn mip_tint(mip: u32) -> vec3<f32> {
+    var tints = array<vec3<f32>, 7>(
+        vec3<f32>(1.0, 0.3, 0.3),
+        vec3<f32>(1.0, 0.7, 0.2),
+        vec3<f32>(1.0, 1.0, 0.3),
+        vec3<f32>(0.3, 1.0, 0.3),
+        vec3<f32>(0.3, 0.8, 1.0),
+        vec3<f32>(0.4, 0.4, 1.0),
+        vec3<f32>(0.9, 0.4, 1.0),
+    );
+    return tints[mip];
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Derivatives first, while control flow is still uniform
+    let texels = in.uv * f32(TILES_PER_SIDE) * TILE_SIZE;
+    let footprint = max(length(dpdx(texels)), length(dpdy(texels)));
+    let lod = clamp(log2(max(footprint, 1e-6)) + uniforms.mip_bias, 0.0, f32(MIP_COUNT - 1u));
+    let wanted = u32(lod);
+
+    // Report the wanted tile from a sparse, per-frame shifted set of pixels
+    let pixel = vec2<u32>(in.clip_position.xy);
+    let phase = uniforms.frame % FEEDBACK_STRIDE;
+    if pixel.x % FEEDBACK_STRIDE == phase && pixel.y % FEEDBACK_STRIDE == (phase * 3u) % FEEDBACK_STRIDE {
+        request(tile_index(in.uv, wanted));
+    }
+
+    // Fall back to coarser mips until a resident tile is found; the
+    // coarsest tile is always resident
+    var mip = wanted;
+    var entry = page_table[tile_index(in.uv, mip)];
+    while entry == 0u && mip + 1u < MIP_COUNT {
+        mip += 1u;
+        entry = page_table[tile_index(in.uv, mip)];
+    }
+    if entry == 0u {
+        return vec4<f32>(1.0, 0.0, 1.0, 1.0);
+    }
+
+    // Position inside the tile, kept half a texel from the edges so that
+    // bilinear filtering never reads the neighboring slot
+    let tiles = f32(tiles_at(mip));
+    let local = clamp(fract(in.uv * tiles) * TILE_SIZE, vec2<f32>(0.5), vec2<f32>(TILE_SIZE - 0.5));
+    let slot = entry - 1u;
+    let origin = vec2<f32>(f32(slot % ATLAS_TILES_PER_ROW), f32(slot / ATLAS_TILES_PER_ROW)) * TILE_SIZE;
+    let atlas_size = f32(ATLAS_TILES_PER_ROW) * TILE_SIZE;
+    var color = textureSampleLevel(atlas, atlas_sampler, (origin + local) / atlas_size, 0.0).rgb;
+
+    if uniforms.show_tiles != 0u {
+        color = mix(color, mip_tint(mip), 0.35);
+        // Texels of the resident mip covered by one pixel
+        let border = 1.5 * footprint / f32(1u << mip);
+        let edge = min(local, vec2<f32>(TILE_SIZE) - local);
+        if min(edge.x, edge.y) < border {
+            color = vec3<f32>(1.0);
+        }
+    }
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+/// Tiles along each side of the virtual texture at `mip`
+pub const fn tiles_at(mip: u32) -> u32 {
+    TILES_PER_SIDE >> mip
+}
+
+/// Index of the first tile of `mip`; the tiles of mip 0 come first
+pub const fn mip_offset(mip: u32) -> u32 {
+    let mut offset = 0;
+    let mut m = 0;
+    while m < mip {
+        offset += tiles_at(m) * tiles_at(m);
+        m += 1;
+    }
+    offset
+}
+
+/// A tile of the virtual texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TileId {
+    pub mip: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl TileId {
+    /// The single tile of the coarsest mip, which is always resident
+    pub const fn coarsest() -> Self {
+        Self {
+            mip: MIP_COUNT - 1,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    /// Index into the page table, as computed by the shader
+    pub fn index(&self) -> u32 {
+        mip_offset(self.mip) + self.y * tiles_at(self.mip) + self.x
+    }
+
+    pub fn from_index(index: u32) -> Option<Self> {
+        (0..MIP_COUNT).find_map(|mip| {
+            let local = index.checked_sub(mip_offset(mip))?;
+            let tiles = tiles_at(mip);
+            (local < tiles * tiles).then_some(Self {
+                mip,
+                x: local % tiles,
+                y: local / tiles,
+            })
+        })
+    }
+
+    /// The tile of the next coarser mip covering this one
+    pub fn parent(&self) -> Option<Self> {
+        (self.mip + 1 < MIP_COUNT).then_some(Self {
+            mip: self.mip + 1,
+            x: self.x / 2,
+            y: self.y / 2,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    tile: TileId,
+    /// Frame in which the tile was last requested
+    last_used: u64,
+    pinned: bool,
+}
+
+/// Assignment of tiles to atlas slots with least-recently-used eviction
+#[derive(Debug, Clone)]
+pub struct TileAtlas {
+    slots: Vec<Option<Slot>>,
+    lookup: HashMap<TileId, u32>,
+}
+
+impl TileAtlas {
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            slots: vec![None; capacity as usize],
+            lookup: HashMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.slots.len() as u32
+    }
+
+    /// Number of resident tiles
+    pub fn len(&self) -> usize {
+        self.lookup.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lookup.is_empty()
+    }
+
+    /// Number of resident tiles of every mip
+    pub fn resident_per_mip(&self) -> [usize; MIP_COUNT as usize] {
+        let mut counts = [0; MIP_COUNT as usize];
+        for tile in self.lookup.keys() {
+            counts[tile.mip as usize] += 1;
+        }
+        counts
+    }
+
+    pub fn slot_of(&self, tile: TileId) -> Option<u32> {
+        self.lookup.get(&tile).copied()
+    }
+
+    /// Mark a resident tile as used in `frame`; false if it is not resident
+    pub fn touch(&mut self, tile: TileId, frame: u64) -> bool {
+        let Some(&slot) = self.lookup.get(&tile) else {
+            return false;
+        };
+        if let Some(entry) = &mut self.slots[slot as usize] {
+            entry.last_used = frame;
+        }
+        true
+    }
+
+    /// Give `tile` a slot, evicting the least recently used tile if needed
+    ///
+    /// Returns the slot and the evicted tile, or `None` when every slot is
+    /// pinned or holds a tile used in `frame` itself.
+    pub fn insert(&mut self, tile: TileId, frame: u64) -> Option<(u32, Option<TileId>)> {
+        if self.touch(tile, frame) {
+            return self.slot_of(tile).map(|slot| (slot, None));
+        }
+        let slot = match self.slots.iter().position(Option::is_none) {
+            Some(free) => free,
+            None => self
+                .slots
+                .iter()
+                .enumerate()
+                .filter_map(|(i, entry)| entry.map(|entry| (i, entry)))
+                .filter(|(_, entry)| !entry.pinned && entry.last_used < frame)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(i, _)| i)?,
+        };
+        let evicted = self.slots[slot].map(|entry| entry.tile);
+        if let Some(evicted) = evicted {
+            self.lookup.remove(&evicted);
+        }
+        self.slots[slot] = Some(Slot {
+            tile,
+            last_used: frame,
+            pinned: false,
+        });
+        self.lookup.insert(tile, slot as u32);
+        Some((slot as u32, evicted))
+    }
+
+    /// Keep a resident tile from ever being evicted
+    pub fn pin(&mut self, tile: TileId) {
+        if let Some(&slot) = self.lookup.get(&tile) {
+            if let Some(entry) = &mut self.slots[slot as usize] {
+                entry.pinned = true;
+            }
+        }
+    }
+}
+
+/// Octaves of the procedural terrain: frequency in u and v, amplitude, phase
+const OCTAVES: [(f32, f32, f32, f32); 5] = [
+    (3.0, 2.0, 0.22, 0.0),
+    (7.0, 9.0, 0.12, 1.3),
+    (23.0, 19.0, 0.06, 2.1),
+    (97.0, 89.0, 0.03, 0.7),
+    (409.0, 397.0, 0.015, 1.9),
+];
+
+/// Terrain height in 0..1, leaving out octaves finer than the texel size
+/// `texel` so that coarse mips are filtered rather than aliased
+fn terrain_height(u: f32, v: f32, texel: f32) -> f32 {
+    let tau = std::f32::consts::TAU;
+    OCTAVES
+        .iter()
+        .filter(|(fu, fv, _, _)| fu.max(*fv) * texel < 0.25)
+        .map(|(fu, fv, amplitude, phase)| {
+            amplitude * (u * fu * tau + phase).sin() * (v * fv * tau + phase * 0.5).cos()
+        })
+        .sum::<f32>()
+        + 0.5
+}
+
+/// Color of the ground at virtual texture coordinate `(u, v)`
+fn ground_color(u: f32, v: f32, texel: f32) -> [u8; 4] {
+    let height = terrain_height(u, v, texel);
+    let [r, g, b] = match height {
+        h if h < 0.32 => [0.10, 0.25, 0.55],
+        h if h < 0.36 => [0.80, 0.75, 0.50],
+        h if h < 0.58 => [0.20, 0.50, 0.15],
+        h if h < 0.72 => [0.45, 0.40, 0.35],
+        _ => [0.95, 0.95, 0.97],
+    };
+    let shade = 0.7 + 0.6 * (height - 0.5);
+
+    // Lines along the mip 0 tile borders, while they are at least 8 texels apart
+    let spacing = 1.0 / TILES_PER_SIDE as f32;
+    let line = spacing >= 8.0 * texel && {
+        let distance = |x: f32| {
+            let cell = (x / spacing).fract();
+            cell.min(1.0 - cell) * spacing
+        };
+        distance(u).min(distance(v)) < texel
+    };
+    let shade = if line { shade * 0.6 } else { shade };
+
+    let to_byte = |c: f32| ((c * shade).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [to_byte(r), to_byte(g), to_byte(b), 255]
+}
+
+/// Generate the RGBA texels of a tile, standing in for a disk or network
+/// read
+pub fn generate_tile(tile: TileId) -> Vec<u8> {
+    let texel = 1.0 / (tiles_at(tile.mip) * TILE_SIZE) as f32;
+    let mut data = Vec::with_capacity(TILE_BYTES);
+    for py in 0..TILE_SIZE {
+        let v = ((tile.y * TILE_SIZE + py) as f32 + 0.5) * texel;
+        for px in 0..TILE_SIZE {
+            let u = ((tile.x * TILE_SIZE + px) as f32 + 0.5) * texel;
+            data.extend_from_slice(&ground_color(u, v, texel));
+        }
+    }
+    data
+}
+
+/// Counters of the streaming loop
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StreamingStats {
+    /// Tiles requested in the latest feedback, including coarser fallbacks
+    pub requested: usize,
+    /// Requested tiles that are not resident yet
+    pub pending: usize,
+    pub uploaded_last_frame: u32,
+    pub uploaded_total: u64,
+    pub evicted_total: u64,
+    /// Feedback readbacks that arrived
+    pub feedback_frames: u64,
+    /// Frames whose feedback was not read back because a readback was
+    /// still in flight
+    pub skipped_feedback: u64,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: Mat4,
+    world_size: f32,
+    frame: u32,
+    mip_bias: f32,
+    show_tiles: u32,
+}
+
+/// Where the feedback readback buffer is in its copy/map cycle
+enum Readback {
+    Idle,
+    /// The copy is recorded but not yet submitted
+    Copied,
+    /// Mapping was requested; the callback stores whether it succeeded
+    Mapping(Arc<Mutex<Option<bool>>>),
+}
+
+/// Renderer of the tiled streaming example, owning the atlas and the
+/// feedback loop
+pub struct TileStreamer {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    atlas_texture: wgpu::Texture,
+    atlas_view: wgpu::TextureView,
+    page_table_buffer: wgpu::Buffer,
+    requested_buffer: wgpu::Buffer,
+    requests_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    readback: Readback,
+    atlas: TileAtlas,
+    /// CPU copy of the page table: atlas slot + 1, or 0 if not resident
+    page_table: Vec<u32>,
+    page_table_dirty: bool,
+    /// Tiles of the latest feedback, coarse mips first
+    wanted: Vec<TileId>,
+    frame: u64,
+    upload_budget: u32,
+    mip_bias: f32,
+    show_tiles: bool,
+    stats: StreamingStats,
+    #[cfg(not(target_arch = "wasm32"))]
+    atlas_texture_id: Option<egui::TextureId>,
+}
+
+impl TileStreamer {
+    /// Create the renderer drawing into `color_format` targets
+    ///
+    /// Only the coarsest tile is resident at first.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Tile Streaming Shader"),
+            source: wgpu::ShaderSource::Wgsl(TILE_STREAMING_SHADER.into()),
+        });
+
+        tracker.record(ApiCategory::Texture, "create_texture");
+        let atlas_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Tile Atlas"),
+            size: wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: ATLAS_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let atlas_view = atlas_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Tile Atlas Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let storage = |label, size, usage| {
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let uniform_buffer = storage(
+            "Tile Streaming Uniforms",
+            std::mem::size_of::<Uniforms>() as u64,
+            wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        );
+        let page_table_buffer = storage(
+            "Tile Page Table",
+            PAGE_TABLE_SIZE,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        let requested_buffer = storage(
+            "Tile Request Flags",
+            PAGE_TABLE_SIZE,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        let requests_buffer = storage(
+            "Tile Requests",
+            REQUESTS_SIZE,
+            wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+        );
+        let readback_buffer = storage(
+            "Tile Requests Readback",
+            REQUESTS_SIZE,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+
+        let buffer_entry = |binding, read_only, size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: wgpu::BufferSize::new(size),
+            },
+            count: None,
+        };
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Tile Streaming Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                buffer_entry(3, true, PAGE_TABLE_SIZE),
+                buffer_entry(4, false, PAGE_TABLE_SIZE),
+                buffer_entry(5, false, REQUESTS_SIZE),
+            ],
+        });
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Tile Streaming Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&atlas_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: page_table_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: requested_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: requests_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Tile Streaming Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+        tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Tile Streaming Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+        });
+
+        let mut streamer = Self {
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            atlas_texture,
+            atlas_view,
+            page_table_buffer,
+            requested_buffer,
+            requests_buffer,
+            readback_buffer,
+            readback: Readback::Idle,
+            atlas: TileAtlas::new(ATLAS_CAPACITY),
+            page_table: vec![0; TILE_COUNT as usize],
+            page_table_dirty: true,
+            wanted: Vec::new(),
+            frame: 0,
+            upload_budget: 8,
+            mip_bias: 0.0,
+            show_tiles: false,
+            stats: StreamingStats::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            atlas_texture_id: None,
+        };
+        streamer.upload(queue, TileId::coarsest());
+        streamer.atlas.pin(TileId::coarsest());
+        streamer
+    }
+
+    /// Tiles generated and uploaded per frame at most
+    pub fn upload_budget(&self) -> u32 {
+        self.upload_budget
+    }
+
+    pub fn set_upload_budget(&mut self, budget: u32) {
+        self.upload_budget = budget;
+    }
+
+    /// Offset added to the mip the shader asks for; positive values
+    /// request coarser tiles
+    pub fn mip_bias(&self) -> f32 {
+        self.mip_bias
+    }
+
+    pub fn set_mip_bias(&mut self, bias: f32) {
+        self.mip_bias = bias;
+    }
+
+    /// Whether tiles are tinted by mip and outlined
+    pub fn show_tiles(&self) -> bool {
+        self.show_tiles
+    }
+
+    pub fn set_show_tiles(&mut self, show: bool) {
+        self.show_tiles = show;
+    }
+
+    pub fn stats(&self) -> StreamingStats {
+        self.stats
+    }
+
+    pub fn atlas(&self) -> &TileAtlas {
+        &self.atlas
+    }
+
+    /// Evict every tile except the coarsest, so streaming starts over
+    pub fn flush(&mut self, queue: &wgpu::Queue) {
+        self.atlas = TileAtlas::new(ATLAS_CAPACITY);
+        self.page_table.fill(0);
+        self.wanted.clear();
+        self.stats = StreamingStats::default();
+        self.upload(queue, TileId::coarsest());
+        self.atlas.pin(TileId::coarsest());
+    }
+
+    /// Draw the ground plane into `target` and read back the tiles it needed
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        view_proj: Mat4,
+        clear_color: wgpu::Color,
+    ) {
+        self.poll_feedback(device);
+        self.stream_tiles(queue);
+
+        let tracker = ApiCoverageTracker::global();
+        if self.page_table_dirty {
+            upload_belt::write_buffer(
+                queue,
+                &self.page_table_buffer,
+                0,
+                bytemuck::cast_slice(&self.page_table),
+            );
+            self.page_table_dirty = false;
+        }
+        let uniforms = Uniforms {
+            view_proj,
+            world_size: WORLD_SIZE,
+            frame: self.frame as u32,
+            mip_bias: self.mip_bias,
+            show_tiles: u32::from(self.show_tiles),
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Tile Streaming Encoder"),
+        });
+        tracker.record(ApiCategory::CommandEncoder, "clear_buffer");
+        encoder.clear_buffer(&self.requested_buffer, 0, None);
+        encoder.clear_buffer(&self.requests_buffer, 0, None);
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Tile Streaming Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..6, 0..1);
+        }
+
+        if matches!(self.readback, Readback::Idle) {
+            tracker.record(ApiCategory::CommandEncoder, "copy_buffer_to_buffer");
+            encoder.copy_buffer_to_buffer(
+                &self.requests_buffer,
+                0,
+                &self.readback_buffer,
+                0,
+                REQUESTS_SIZE,
+            );
+            self.readback = Readback::Copied;
+        } else {
+            self.stats.skipped_feedback += 1;
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+        self.frame += 1;
+
+        if matches!(self.readback, Readback::Copied) {
+            let mapped = Arc::new(Mutex::new(None));
+            let callback_mapped = mapped.clone();
+            self.readback_buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    *callback_mapped.lock().unwrap_or_else(|e| e.into_inner()) =
+                        Some(result.is_ok());
+                });
+            self.readback = Readback::Mapping(mapped);
+        }
+    }
+
+    /// Wait until the feedback of the last rendered frame has arrived
+    ///
+    /// Meant for tests; the frame loop picks feedback up without blocking.
+    pub fn wait_for_feedback(&mut self, device: &wgpu::Device) -> bool {
+        if !matches!(self.readback, Readback::Mapping(_)) {
+            return false;
+        }
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+        self.poll_feedback(device)
+    }
+
+    /// Collect a finished feedback readback without blocking
+    fn poll_feedback(&mut self, device: &wgpu::Device) -> bool {
+        let Readback::Mapping(mapped) = &self.readback else {
+            return false;
+        };
+        let _ = device.poll(wgpu::PollType::Poll);
+        let Some(success) = *mapped.lock().unwrap_or_else(|e| e.into_inner()) else {
+            return false;
+        };
+        self.readback = Readback::Idle;
+        if !success {
+            log::warn!("Failed to map the tile request readback buffer");
+            return false;
+        }
+
+        let ids: Vec<u32> = {
+            let data = self.readback_buffer.slice(..).get_mapped_range();
+            let words: &[u32] = bytemuck::cast_slice(&data);
+            let count = (words[0] as usize).min(MAX_REQUESTS as usize);
+            words[1..=count].to_vec()
+        };
+        self.readback_buffer.unmap();
+
+        // A tile is only useful with its ancestors resident too: they are
+        // the fallback while it streams in and after it is evicted
+        let mut wanted = BTreeSet::new();
+        for tile in ids.into_iter().filter_map(TileId::from_index) {
+            let mut next = Some(tile);
+            while let Some(tile) = next {
+                if !wanted.insert(tile) {
+                    break;
+                }
+                next = tile.parent();
+            }
+        }
+        // Coarse tiles first, so that a small budget still covers the view
+        self.wanted = wanted.into_iter().collect();
+        self.wanted
+            .sort_by_key(|tile| (std::cmp::Reverse(tile.mip), tile.y, tile.x));
+        self.stats.requested = self.wanted.len();
+        self.stats.feedback_frames += 1;
+        true
+    }
+
+    /// Refresh the wanted resident tiles and upload missing ones within
+    /// the budget
+    fn stream_tiles(&mut self, queue: &wgpu::Queue) {
+        let frame = self.frame;
+        let mut missing = Vec::new();
+        for &tile in &self.wanted {
+            if !self.atlas.touch(tile, frame) {
+                missing.push(tile);
+            }
+        }
+
+        let mut uploaded = 0;
+        for &tile in &missing {
+            if uploaded == self.upload_budget || !self.upload(queue, tile) {
+                break;
+            }
+            uploaded += 1;
+        }
+        self.stats.pending = missing.len() - uploaded as usize;
+        self.stats.uploaded_last_frame = uploaded;
+        self.stats.uploaded_total += u64::from(uploaded);
+    }
+
+    /// Generate `tile` and copy it into an atlas slot; false if the atlas
+    /// is full of tiles needed this frame
+    fn upload(&mut self, queue: &wgpu::Queue, tile: TileId) -> bool {
+        let Some((slot, evicted)) = self.atlas.insert(tile, self.frame) else {
+            return false;
+        };
+        if let Some(evicted) = evicted {
+            self.page_table[evicted.index() as usize] = 0;
+            self.stats.evicted_total += 1;
+        }
+        self.page_table[tile.index() as usize] = slot + 1;
+        self.page_table_dirty = true;
+
+        ApiCoverageTracker::global().record(ApiCategory::Queue, "write_texture");
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.atlas_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: slot % ATLAS_TILES_PER_ROW * TILE_SIZE,
+                    y: slot / ATLAS_TILES_PER_ROW * TILE_SIZE,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &generate_tile(tile),
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(TILE_SIZE * 4),
+                rows_per_image: Some(TILE_SIZE),
+            },
+            wgpu::Extent3d {
+                width: TILE_SIZE,
+                height: TILE_SIZE,
+                depth_or_array_layers: 1,
+            },
+        );
+        true
+    }
+
+    /// Texture ID of the atlas for display in egui
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn atlas_texture_id(
+        &mut self,
+        device: &wgpu::Device,
+        renderer: &mut egui_wgpu::Renderer,
+    ) -> egui::TextureId {
+        *self.atlas_texture_id.get_or_insert_with(|| {
+            renderer.register_native_texture(
+                device,
+                &self.atlas_view,
+                egui_wgpu::wgpu::FilterMode::Linear,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_count() {
+        assert_eq!(TILE_COUNT, 4096 + 1024 + 256 + 64 + 16 + 4 + 1);
+        assert_eq!(tiles_at(MIP_COUNT - 1), 1);
+        assert_eq!(TileId::coarsest().index(), TILE_COUNT - 1);
+    }
+
+    #[test]
+    fn test_shader_constants_match() {
+        for declaration in [
+            format!("const TILE_SIZE: f32 = {}.0;", TILE_SIZE),
+            format!("const TILES_PER_SIDE: u32 = {}u;", TILES_PER_SIDE),
+            format!("const MIP_COUNT: u32 = {}u;", MIP_COUNT),
+            format!("const TILE_COUNT: u32 = {}u;", TILE_COUNT),
+            format!("const ATLAS_TILES_PER_ROW: u32 = {}u;", ATLAS_TILES_PER_ROW),
+            format!("const MAX_REQUESTS: u32 = {}u;", MAX_REQUESTS),
+            format!("const FEEDBACK_STRIDE: u32 = {}u;", FEEDBACK_STRIDE),
+        ] {
+            assert!(
+                TILE_STREAMING_SHADER.contains(&declaration),
+                "shader lacks `{}`",
+                declaration
+            );
+        }
+        assert_eq!(std::mem::size_of::<Uniforms>(), 80);
+    }
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(TILE_STREAMING_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+    }
+
+    #[test]
+    fn test_tile_index_round_trip() {
+        for index in [0, 1, 63, 64, 4095, 4096, 5119, 5120, TILE_COUNT - 1] {
+            let tile = TileId::from_index(index).unwrap();
+            assert_eq!(tile.index(), index);
+        }
+        assert_eq!(TileId::from_index(TILE_COUNT), None);
+        assert_eq!(
+            TileId::from_index(4096),
+            Some(TileId { mip: 1, x: 0, y: 0 })
+        );
+    }
+
+    #[test]
+    fn test_parent_chain_ends_at_coarsest() {
+        let mut tile = TileId {
+            mip: 0,
+            x: 37,
+            y: 12,
+        };
+        let mut steps = 0;
+        while let Some(parent) = tile.parent() {
+            assert_eq!((parent.x, parent.y), (tile.x / 2, tile.y / 2));
+            tile = parent;
+            steps += 1;
+        }
+        assert_eq!(steps, MIP_COUNT - 1);
+        assert_eq!(tile, TileId::coarsest());
+    }
+
+    #[test]
+    fn test_atlas_evicts_least_recently_used() {
+        let tile = |x| TileId { mip: 0, x, y: 0 };
+        let mut atlas = TileAtlas::new(2);
+        assert_eq!(atlas.insert(tile(0), 0), Some((0, None)));
+        assert_eq!(atlas.insert(tile(1), 1), Some((1, None)));
+        assert!(atlas.touch(tile(0), 2));
+
+        assert_eq!(atlas.insert(tile(2), 3), Some((1, Some(tile(1)))));
+        assert_eq!(atlas.slot_of(tile(1)), None);
+        assert_eq!(atlas.slot_of(tile(2)), Some(1));
+        assert_eq!(atlas.len(), 2);
+        assert_eq!(atlas.resident_per_mip()[0], 2);
+    }
+
+    #[test]
+    fn test_atlas_keeps_pinned_and_current_tiles() {
+        let tile = |x| TileId { mip: 0, x, y: 0 };
+        let mut atlas = TileAtlas::new(2);
+        atlas.insert(tile(0), 0);
+        atlas.pin(tile(0));
+        atlas.insert(tile(1), 5);
+
+        // Tile 1 was used in frame 5 itself and tile 0 is pinned
+        assert_eq!(atlas.insert(tile(2), 5), None);
+        assert_eq!(atlas.insert(tile(2), 6), Some((1, Some(tile(1)))));
+        assert_eq!(atlas.slot_of(tile(0)), Some(0));
+    }
+
+    #[test]
+    fn test_generated_tiles() {
+        let data = generate_tile(TileId::coarsest());
+        assert_eq!(data.len(), TILE_BYTES);
+        assert!(data.chunks(4).all(|texel| texel[3] == 255));
+
+        let left = generate_tile(TileId {
+            mip: 0,
+            x: 10,
+            y: 10,
+        });
+        let right = generate_tile(TileId {
+            mip: 0,
+            x: 11,
+            y: 10,
+        });
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn test_coarse_tiles_drop_fine_octaves() {
+        let fine = 1.0 / (TILES_PER_SIDE * TILE_SIZE) as f32;
+        let coarse = 1.0 / TILE_SIZE as f32;
+        let variation = |texel: f32| {
+            (0..64)
+                .map(|i| terrain_height(i as f32 * 0.0005, 0.3, texel))
+                .fold((f32::MAX, f32::MIN), |(lo, hi), h| (lo.min(h), hi.max(h)))
+        };
+        let (fine_lo, fine_hi) = variation(fine);
+        let (coarse_lo, coarse_hi) = variation(coarse);
+        assert!(fine_hi - fine_lo > coarse_hi - coarse_lo);
+    }
+}
@@ -1,8 +1,10 @@
 //! Tooltip utilities for WebGPU UI controls with specification links
 //!
 //! This module provides helpers for creating rich tooltips that explain WebGPU concepts
-//! and link to relevant sections of the WebGPU specification.
+//! and link to relevant sections of the WebGPU specification. The descriptions are
+//! messages in the [`i18n`](crate::i18n) catalogs, shown in the selected language.
 
+use crate::i18n::tr;
 use egui::{Response, RichText};
 
 /// WebGPU specification base URL
@@ -28,8 +30,8 @@ impl TooltipExt for Response {
                     ui.label(RichText::new("📄").size(12.0));
                     let url = format!("{}{}", WEBGPU_SPEC_BASE, anchor);
                     if ui
-                        .hyperlink_to("WebGPU Spec", &url)
-                        .on_hover_text("Click to open specification")
+                        .hyperlink_to(tr("tooltip-spec-link"), &url)
+                        .on_hover_text(tr("tooltip-spec-link-hover"))
                         .clicked()
                     {
                         #[cfg(not(target_arch = "wasm32"))]
@@ -51,24 +53,26 @@ impl TooltipExt for Response {
 
 /// Tooltip information for common WebGPU concepts
 pub struct TooltipInfo {
-    /// Human-readable description
-    pub description: &'static str,
+    /// Message key of the human-readable description
+    pub key: &'static str,
     /// Optional WebGPU specification anchor
     pub spec_anchor: Option<&'static str>,
 }
 
 impl TooltipInfo {
     /// Create a new tooltip info
-    pub const fn new(description: &'static str, spec_anchor: Option<&'static str>) -> Self {
-        Self {
-            description,
-            spec_anchor,
-        }
+    pub const fn new(key: &'static str, spec_anchor: Option<&'static str>) -> Self {
+        Self { key, spec_anchor }
+    }
+
+    /// Description in the selected language
+    pub fn description(&self) -> String {
+        tr(self.key)
     }
 
     /// Apply this tooltip to a response
     pub fn apply(&self, response: Response) -> Response {
-        response.webgpu_tooltip(self.description, self.spec_anchor)
+        response.webgpu_tooltip(&self.description(), self.spec_anchor)
     }
 }
 
@@ -77,52 +81,52 @@ pub mod buffer_usage {
     use super::TooltipInfo;
 
     pub const VERTEX: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used as a vertex buffer in draw commands. Contains per-vertex attributes like position, normal, UV coordinates, etc.",
+        "tooltip-buffer-usage-vertex",
         Some("#dom-gpubufferusage-vertex"),
     );
 
     pub const INDEX: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used as an index buffer in indexed draw commands. Contains indices that reference vertices in vertex buffers.",
+        "tooltip-buffer-usage-index",
         Some("#dom-gpubufferusage-index"),
     );
 
     pub const UNIFORM: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used as a uniform buffer. Contains read-only data accessible to shaders, typically for global parameters like transformation matrices.",
+        "tooltip-buffer-usage-uniform",
         Some("#dom-gpubufferusage-uniform"),
     );
 
     pub const STORAGE: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used as a storage buffer. Allows shaders to read and write arbitrary data, useful for compute operations and large datasets.",
+        "tooltip-buffer-usage-storage",
         Some("#dom-gpubufferusage-storage"),
     );
 
     pub const INDIRECT: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used for indirect draw commands. Contains draw parameters that can be dynamically determined on the GPU.",
+        "tooltip-buffer-usage-indirect",
         Some("#dom-gpubufferusage-indirect"),
     );
 
     pub const COPY_SRC: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used as the source of a copy operation. Allows reading data from this buffer to copy to another buffer or texture.",
+        "tooltip-buffer-usage-copy-src",
         Some("#dom-gpubufferusage-copy_src"),
     );
 
     pub const COPY_DST: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used as the destination of a copy operation. Allows writing data to this buffer from another buffer, texture, or the CPU.",
+        "tooltip-buffer-usage-copy-dst",
         Some("#dom-gpubufferusage-copy_dst"),
     );
 
     pub const MAP_READ: TooltipInfo = TooltipInfo::new(
-        "Buffer can be mapped for reading on the CPU. Allows reading GPU data back to the CPU, but cannot be used with MAP_WRITE.",
+        "tooltip-buffer-usage-map-read",
         Some("#dom-gpubufferusage-map_read"),
     );
 
     pub const MAP_WRITE: TooltipInfo = TooltipInfo::new(
-        "Buffer can be mapped for writing from the CPU. Allows uploading data to the GPU, but cannot be used with MAP_READ.",
+        "tooltip-buffer-usage-map-write",
         Some("#dom-gpubufferusage-map_write"),
     );
 
     pub const QUERY_RESOLVE: TooltipInfo = TooltipInfo::new(
-        "Buffer can be used to resolve query results. Stores query data like timestamps or occlusion test results.",
+        "tooltip-buffer-usage-query-resolve",
         Some("#dom-gpubufferusage-query_resolve"),
     );
 }
@@ -132,27 +136,27 @@ pub mod texture_usage {
     use super::TooltipInfo;
 
     pub const COPY_SRC: TooltipInfo = TooltipInfo::new(
-        "Texture can be used as the source of a copy operation. Allows reading texture data to copy to another texture or buffer.",
+        "tooltip-texture-usage-copy-src",
         Some("#dom-gputextureusage-copy_src"),
     );
 
     pub const COPY_DST: TooltipInfo = TooltipInfo::new(
-        "Texture can be used as the destination of a copy operation. Allows writing data to this texture from a buffer, another texture, or image uploads.",
+        "tooltip-texture-usage-copy-dst",
         Some("#dom-gputextureusage-copy_dst"),
     );
 
     pub const TEXTURE_BINDING: TooltipInfo = TooltipInfo::new(
-        "Texture can be bound to shaders for sampling. Allows shaders to read and sample texture data using samplers.",
+        "tooltip-texture-usage-texture-binding",
         Some("#dom-gputextureusage-texture_binding"),
     );
 
     pub const STORAGE_BINDING: TooltipInfo = TooltipInfo::new(
-        "Texture can be bound as a storage texture. Allows shaders to read and write texture data directly without sampling, useful for compute operations.",
+        "tooltip-texture-usage-storage-binding",
         Some("#dom-gputextureusage-storage_binding"),
     );
 
     pub const RENDER_ATTACHMENT: TooltipInfo = TooltipInfo::new(
-        "Texture can be used as a render target. Allows rendering operations to write to this texture as a color, depth, or stencil attachment.",
+        "tooltip-texture-usage-render-attachment",
         Some("#dom-gputextureusage-render_attachment"),
     );
 }
@@ -162,27 +166,27 @@ pub mod primitive_topology {
     use super::TooltipInfo;
 
     pub const POINT_LIST: TooltipInfo = TooltipInfo::new(
-        "Each vertex represents a point. Useful for particle systems and point clouds.",
+        "tooltip-primitive-topology-point-list",
         Some("#dom-gpuprimitivetopology-point-list"),
     );
 
     pub const LINE_LIST: TooltipInfo = TooltipInfo::new(
-        "Every two vertices form an independent line segment. Useful for wireframe rendering and debug visualization.",
+        "tooltip-primitive-topology-line-list",
         Some("#dom-gpuprimitivetopology-line-list"),
     );
 
     pub const LINE_STRIP: TooltipInfo = TooltipInfo::new(
-        "Vertices form a connected sequence of lines. Each vertex connects to the next, creating a polyline.",
+        "tooltip-primitive-topology-line-strip",
         Some("#dom-gpuprimitivetopology-line-strip"),
     );
 
     pub const TRIANGLE_LIST: TooltipInfo = TooltipInfo::new(
-        "Every three vertices form an independent triangle. The most common topology for 3D rendering.",
+        "tooltip-primitive-topology-triangle-list",
         Some("#dom-gpuprimitivetopology-triangle-list"),
     );
 
     pub const TRIANGLE_STRIP: TooltipInfo = TooltipInfo::new(
-        "Vertices form a connected strip of triangles. More memory-efficient for certain mesh types like grids.",
+        "tooltip-primitive-topology-triangle-strip",
         Some("#dom-gpuprimitivetopology-triangle-strip"),
     );
 }
@@ -191,35 +195,36 @@ pub mod primitive_topology {
 pub mod cull_mode {
     use super::TooltipInfo;
 
-    pub const NONE: TooltipInfo = TooltipInfo::new(
-        "No face culling. Both front and back faces are rendered. Useful for 2D objects or double-sided geometry.",
-        Some("#dom-gpucullmode-none"),
-    );
+    pub const NONE: TooltipInfo =
+        TooltipInfo::new("tooltip-cull-mode-none", Some("#dom-gpucullmode-none"));
 
-    pub const FRONT: TooltipInfo = TooltipInfo::new(
-        "Cull front-facing triangles. Only back faces are rendered. Rarely used in practice.",
-        Some("#dom-gpucullmode-front"),
-    );
+    pub const FRONT: TooltipInfo =
+        TooltipInfo::new("tooltip-cull-mode-front", Some("#dom-gpucullmode-front"));
 
-    pub const BACK: TooltipInfo = TooltipInfo::new(
-        "Cull back-facing triangles. Only front faces are rendered. The most common culling mode for 3D objects.",
-        Some("#dom-gpucullmode-back"),
-    );
+    pub const BACK: TooltipInfo =
+        TooltipInfo::new("tooltip-cull-mode-back", Some("#dom-gpucullmode-back"));
+}
+
+/// Polygon mode tooltips
+pub mod polygon_mode {
+    use super::TooltipInfo;
+
+    pub const FILL: TooltipInfo = TooltipInfo::new("tooltip-polygon-mode-fill", None);
+
+    pub const LINE: TooltipInfo = TooltipInfo::new("tooltip-polygon-mode-line", None);
+
+    pub const POINT: TooltipInfo = TooltipInfo::new("tooltip-polygon-mode-point", None);
 }
 
 /// Front face winding order tooltips
 pub mod front_face {
     use super::TooltipInfo;
 
-    pub const CCW: TooltipInfo = TooltipInfo::new(
-        "Counter-clockwise winding order defines front faces. Vertices ordered counter-clockwise (when viewed from the camera) are front-facing. This is the most common convention.",
-        Some("#dom-gpufrontface-ccw"),
-    );
+    pub const CCW: TooltipInfo =
+        TooltipInfo::new("tooltip-front-face-ccw", Some("#dom-gpufrontface-ccw"));
 
-    pub const CW: TooltipInfo = TooltipInfo::new(
-        "Clockwise winding order defines front faces. Vertices ordered clockwise (when viewed from the camera) are front-facing.",
-        Some("#dom-gpufrontface-cw"),
-    );
+    pub const CW: TooltipInfo =
+        TooltipInfo::new("tooltip-front-face-cw", Some("#dom-gpufrontface-cw"));
 }
 
 /// Compare function tooltips (for depth/stencil testing)
@@ -227,42 +232,42 @@ pub mod compare_function {
     use super::TooltipInfo;
 
     pub const NEVER: TooltipInfo = TooltipInfo::new(
-        "Comparison always fails. No fragments pass the test.",
+        "tooltip-compare-function-never",
         Some("#dom-gpucomparefunction-never"),
     );
 
     pub const LESS: TooltipInfo = TooltipInfo::new(
-        "Comparison passes if the new value is less than the stored value. Most common for depth testing.",
+        "tooltip-compare-function-less",
         Some("#dom-gpucomparefunction-less"),
     );
 
     pub const EQUAL: TooltipInfo = TooltipInfo::new(
-        "Comparison passes if the new value equals the stored value.",
+        "tooltip-compare-function-equal",
         Some("#dom-gpucomparefunction-equal"),
     );
 
     pub const LESS_EQUAL: TooltipInfo = TooltipInfo::new(
-        "Comparison passes if the new value is less than or equal to the stored value.",
+        "tooltip-compare-function-less-equal",
         Some("#dom-gpucomparefunction-less-equal"),
     );
 
     pub const GREATER: TooltipInfo = TooltipInfo::new(
-        "Comparison passes if the new value is greater than the stored value. Used for reverse-Z depth testing.",
+        "tooltip-compare-function-greater",
         Some("#dom-gpucomparefunction-greater"),
     );
 
     pub const NOT_EQUAL: TooltipInfo = TooltipInfo::new(
-        "Comparison passes if the new value does not equal the stored value.",
+        "tooltip-compare-function-not-equal",
         Some("#dom-gpucomparefunction-not-equal"),
     );
 
     pub const GREATER_EQUAL: TooltipInfo = TooltipInfo::new(
-        "Comparison passes if the new value is greater than or equal to the stored value.",
+        "tooltip-compare-function-greater-equal",
         Some("#dom-gpucomparefunction-greater-equal"),
     );
 
     pub const ALWAYS: TooltipInfo = TooltipInfo::new(
-        "Comparison always passes. All fragments pass the test.",
+        "tooltip-compare-function-always",
         Some("#dom-gpucomparefunction-always"),
     );
 }
@@ -272,52 +277,46 @@ pub mod blend_factor {
     use super::TooltipInfo;
 
     pub const ZERO: TooltipInfo = TooltipInfo::new(
-        "Blend factor is 0. The source or destination color is multiplied by zero.",
+        "tooltip-blend-factor-zero",
         Some("#dom-gpublendfactor-zero"),
     );
 
-    pub const ONE: TooltipInfo = TooltipInfo::new(
-        "Blend factor is 1. The source or destination color is used as-is.",
-        Some("#dom-gpublendfactor-one"),
-    );
+    pub const ONE: TooltipInfo =
+        TooltipInfo::new("tooltip-blend-factor-one", Some("#dom-gpublendfactor-one"));
 
-    pub const SRC: TooltipInfo = TooltipInfo::new(
-        "Blend factor is the source color.",
-        Some("#dom-gpublendfactor-src"),
-    );
+    pub const SRC: TooltipInfo =
+        TooltipInfo::new("tooltip-blend-factor-src", Some("#dom-gpublendfactor-src"));
 
     pub const ONE_MINUS_SRC: TooltipInfo = TooltipInfo::new(
-        "Blend factor is 1 minus the source color.",
+        "tooltip-blend-factor-one-minus-src",
         Some("#dom-gpublendfactor-one-minus-src"),
     );
 
     pub const SRC_ALPHA: TooltipInfo = TooltipInfo::new(
-        "Blend factor is the source alpha.",
+        "tooltip-blend-factor-src-alpha",
         Some("#dom-gpublendfactor-src-alpha"),
     );
 
     pub const ONE_MINUS_SRC_ALPHA: TooltipInfo = TooltipInfo::new(
-        "Blend factor is 1 minus the source alpha. Common for alpha blending.",
+        "tooltip-blend-factor-one-minus-src-alpha",
         Some("#dom-gpublendfactor-one-minus-src-alpha"),
     );
 
-    pub const DST: TooltipInfo = TooltipInfo::new(
-        "Blend factor is the destination color.",
-        Some("#dom-gpublendfactor-dst"),
-    );
+    pub const DST: TooltipInfo =
+        TooltipInfo::new("tooltip-blend-factor-dst", Some("#dom-gpublendfactor-dst"));
 
     pub const ONE_MINUS_DST: TooltipInfo = TooltipInfo::new(
-        "Blend factor is 1 minus the destination color.",
+        "tooltip-blend-factor-one-minus-dst",
         Some("#dom-gpublendfactor-one-minus-dst"),
     );
 
     pub const DST_ALPHA: TooltipInfo = TooltipInfo::new(
-        "Blend factor is the destination alpha.",
+        "tooltip-blend-factor-dst-alpha",
         Some("#dom-gpublendfactor-dst-alpha"),
     );
 
     pub const ONE_MINUS_DST_ALPHA: TooltipInfo = TooltipInfo::new(
-        "Blend factor is 1 minus the destination alpha.",
+        "tooltip-blend-factor-one-minus-dst-alpha",
         Some("#dom-gpublendfactor-one-minus-dst-alpha"),
     );
 }
@@ -327,27 +326,27 @@ pub mod blend_operation {
     use super::TooltipInfo;
 
     pub const ADD: TooltipInfo = TooltipInfo::new(
-        "Add source and destination colors. Most common blend operation.",
+        "tooltip-blend-operation-add",
         Some("#dom-gpublendoperation-add"),
     );
 
     pub const SUBTRACT: TooltipInfo = TooltipInfo::new(
-        "Subtract destination from source color.",
+        "tooltip-blend-operation-subtract",
         Some("#dom-gpublendoperation-subtract"),
     );
 
     pub const REVERSE_SUBTRACT: TooltipInfo = TooltipInfo::new(
-        "Subtract source from destination color.",
+        "tooltip-blend-operation-reverse-subtract",
         Some("#dom-gpublendoperation-reverse-subtract"),
     );
 
     pub const MIN: TooltipInfo = TooltipInfo::new(
-        "Use the minimum of source and destination colors.",
+        "tooltip-blend-operation-min",
         Some("#dom-gpublendoperation-min"),
     );
 
     pub const MAX: TooltipInfo = TooltipInfo::new(
-        "Use the maximum of source and destination colors.",
+        "tooltip-blend-operation-max",
         Some("#dom-gpublendoperation-max"),
     );
 }
@@ -357,22 +356,22 @@ pub mod address_mode {
     use super::TooltipInfo;
 
     pub const CLAMP_TO_EDGE: TooltipInfo = TooltipInfo::new(
-        "Texture coordinates are clamped to [0, 1]. Coordinates outside this range use the edge color.",
+        "tooltip-address-mode-clamp-to-edge",
         Some("#dom-gpuaddressmode-clamp-to-edge"),
     );
 
     pub const REPEAT: TooltipInfo = TooltipInfo::new(
-        "Texture coordinates wrap around. Creates a tiling effect where the texture repeats.",
+        "tooltip-address-mode-repeat",
         Some("#dom-gpuaddressmode-repeat"),
     );
 
     pub const MIRROR_REPEAT: TooltipInfo = TooltipInfo::new(
-        "Texture coordinates wrap around with mirroring. The texture alternates between normal and mirrored on each repetition.",
+        "tooltip-address-mode-mirror-repeat",
         Some("#dom-gpuaddressmode-mirror-repeat"),
     );
 
     pub const CLAMP_TO_BORDER: TooltipInfo = TooltipInfo::new(
-        "Texture coordinates outside [0, 1] use the border color.",
+        "tooltip-address-mode-clamp-to-border",
         Some("#dom-gpuaddressmode-clamp-to-border"),
     );
 }
@@ -382,12 +381,12 @@ pub mod filter_mode {
     use super::TooltipInfo;
 
     pub const NEAREST: TooltipInfo = TooltipInfo::new(
-        "Nearest neighbor filtering. Fast but can appear pixelated. Uses the color of the closest texel.",
+        "tooltip-filter-mode-nearest",
         Some("#dom-gpufiltermode-nearest"),
     );
 
     pub const LINEAR: TooltipInfo = TooltipInfo::new(
-        "Linear (bilinear) filtering. Smoother appearance. Interpolates between nearby texels.",
+        "tooltip-filter-mode-linear",
         Some("#dom-gpufiltermode-linear"),
     );
 }
@@ -397,22 +396,22 @@ pub mod sampler {
     use super::TooltipInfo;
 
     pub const LOD_MIN_CLAMP: TooltipInfo = TooltipInfo::new(
-        "Minimum level of detail (LOD) to use when sampling. Lower values use higher resolution mipmap levels. Typically 0.0 to use the highest resolution.",
+        "tooltip-sampler-lod-min-clamp",
         Some("#dom-gpusamplerdescriptor-lodminclamp"),
     );
 
     pub const LOD_MAX_CLAMP: TooltipInfo = TooltipInfo::new(
-        "Maximum level of detail (LOD) to use when sampling. Higher values allow using lower resolution mipmap levels. Common values are 32.0 or the actual mip level count.",
+        "tooltip-sampler-lod-max-clamp",
         Some("#dom-gpusamplerdescriptor-lodmaxclamp"),
     );
 
     pub const MAX_ANISOTROPY: TooltipInfo = TooltipInfo::new(
-        "Maximum anisotropy level for anisotropic filtering. Higher values (up to 16) improve texture quality at oblique viewing angles but may reduce performance. A value of 1 disables anisotropic filtering.",
+        "tooltip-sampler-max-anisotropy",
         Some("#dom-gpusamplerdescriptor-maxanisotropy"),
     );
 
     pub const BORDER_COLOR: TooltipInfo = TooltipInfo::new(
-        "The color to use when the address mode is ClampToBorder and texture coordinates fall outside [0, 1]. Choose from predefined colors: transparent black, opaque black, opaque white, or zero.",
+        "tooltip-sampler-border-color",
         Some("#dom-gpusamplerdescriptor-bordercolor"),
     );
 }
@@ -422,42 +421,42 @@ pub mod stencil_operation {
     use super::TooltipInfo;
 
     pub const KEEP: TooltipInfo = TooltipInfo::new(
-        "Keep the current stencil value unchanged.",
+        "tooltip-stencil-operation-keep",
         Some("#dom-gpustenciloperation-keep"),
     );
 
     pub const ZERO: TooltipInfo = TooltipInfo::new(
-        "Set the stencil value to 0.",
+        "tooltip-stencil-operation-zero",
         Some("#dom-gpustenciloperation-zero"),
     );
 
     pub const REPLACE: TooltipInfo = TooltipInfo::new(
-        "Replace the stencil value with the reference value.",
+        "tooltip-stencil-operation-replace",
         Some("#dom-gpustenciloperation-replace"),
     );
 
     pub const INVERT: TooltipInfo = TooltipInfo::new(
-        "Bitwise invert the stencil value.",
+        "tooltip-stencil-operation-invert",
         Some("#dom-gpustenciloperation-invert"),
     );
 
     pub const INCREMENT_CLAMP: TooltipInfo = TooltipInfo::new(
-        "Increment the stencil value, clamping to the maximum value.",
+        "tooltip-stencil-operation-increment-clamp",
         Some("#dom-gpustenciloperation-increment-clamp"),
     );
 
     pub const DECREMENT_CLAMP: TooltipInfo = TooltipInfo::new(
-        "Decrement the stencil value, clamping to 0.",
+        "tooltip-stencil-operation-decrement-clamp",
         Some("#dom-gpustenciloperation-decrement-clamp"),
     );
 
     pub const INCREMENT_WRAP: TooltipInfo = TooltipInfo::new(
-        "Increment the stencil value, wrapping to 0 on overflow.",
+        "tooltip-stencil-operation-increment-wrap",
         Some("#dom-gpustenciloperation-increment-wrap"),
     );
 
     pub const DECREMENT_WRAP: TooltipInfo = TooltipInfo::new(
-        "Decrement the stencil value, wrapping to the maximum value on underflow.",
+        "tooltip-stencil-operation-decrement-wrap",
         Some("#dom-gpustenciloperation-decrement-wrap"),
     );
 }
@@ -467,52 +466,52 @@ pub mod property {
     use super::TooltipInfo;
 
     pub const BUFFER_SIZE: TooltipInfo = TooltipInfo::new(
-        "Size of the buffer in bytes. Must be greater than 0 and aligned to the usage requirements.",
+        "tooltip-property-buffer-size",
         Some("#dom-gpubufferdescriptor-size"),
     );
 
     pub const BUFFER_MAPPED_AT_CREATION: TooltipInfo = TooltipInfo::new(
-        "Whether the buffer should be mapped immediately after creation. Allows writing initial data without a separate map operation.",
+        "tooltip-property-buffer-mapped-at-creation",
         Some("#dom-gpubufferdescriptor-mappedatcreation"),
     );
 
     pub const TEXTURE_WIDTH: TooltipInfo = TooltipInfo::new(
-        "Width of the texture in pixels. Must be greater than 0.",
+        "tooltip-property-texture-width",
         Some("#dom-gpuextent3ddict-width"),
     );
 
     pub const TEXTURE_HEIGHT: TooltipInfo = TooltipInfo::new(
-        "Height of the texture in pixels. Must be greater than 0.",
+        "tooltip-property-texture-height",
         Some("#dom-gpuextent3ddict-height"),
     );
 
     pub const TEXTURE_DEPTH: TooltipInfo = TooltipInfo::new(
-        "Depth or array layer count. For 2D textures, this is the number of array layers. For 3D textures, this is the depth.",
+        "tooltip-property-texture-depth",
         Some("#dom-gpuextent3ddict-depthorarraylayers"),
     );
 
     pub const TEXTURE_MIP_LEVELS: TooltipInfo = TooltipInfo::new(
-        "Number of mip levels. Mip levels are progressively smaller versions of the texture used for improved rendering quality at different distances.",
+        "tooltip-property-texture-mip-levels",
         Some("#dom-gputexturedescriptor-miplevelcount"),
     );
 
     pub const TEXTURE_SAMPLE_COUNT: TooltipInfo = TooltipInfo::new(
-        "Number of samples per pixel for multisampling. Must be 1 (no multisampling) or a power of 2 up to the device's maximum.",
+        "tooltip-property-texture-sample-count",
         Some("#dom-gputexturedescriptor-samplecount"),
     );
 
     pub const DEPTH_WRITE_ENABLED: TooltipInfo = TooltipInfo::new(
-        "Whether fragments can write to the depth buffer. Usually enabled for opaque objects and disabled for transparent ones.",
+        "tooltip-property-depth-write-enabled",
         Some("#dom-gpudepthstencilstate-depthwriteenabled"),
     );
 
     pub const ALPHA_TO_COVERAGE: TooltipInfo = TooltipInfo::new(
-        "Whether to use the alpha channel to determine coverage for multisampled rendering. Useful for foliage and transparent textures.",
+        "tooltip-property-alpha-to-coverage",
         Some("#dom-gpumultisamplestate-alphatocoverageenabled"),
     );
 
     pub const SAMPLE_COUNT: TooltipInfo = TooltipInfo::new(
-        "Number of samples per pixel for multisampling anti-aliasing (MSAA). Higher values provide better quality but require more memory.",
+        "tooltip-property-sample-count",
         Some("#dom-gpumultisamplestate-count"),
     );
 }
@@ -522,17 +521,17 @@ pub mod shader_visibility {
     use super::TooltipInfo;
 
     pub const VERTEX: TooltipInfo = TooltipInfo::new(
-        "Resource is visible to vertex shaders. Vertex shaders process each vertex of geometry.",
+        "tooltip-shader-visibility-vertex",
         Some("#dom-gpushadervisibility-vertex"),
     );
 
     pub const FRAGMENT: TooltipInfo = TooltipInfo::new(
-        "Resource is visible to fragment shaders. Fragment shaders determine the color of each pixel.",
+        "tooltip-shader-visibility-fragment",
         Some("#dom-gpushadervisibility-fragment"),
     );
 
     pub const COMPUTE: TooltipInfo = TooltipInfo::new(
-        "Resource is visible to compute shaders. Compute shaders perform general-purpose GPU computation.",
+        "tooltip-shader-visibility-compute",
         Some("#dom-gpushadervisibility-compute"),
     );
 }
@@ -542,27 +541,27 @@ pub mod compute {
     use super::TooltipInfo;
 
     pub const WORKGROUP_COUNT_X: TooltipInfo = TooltipInfo::new(
-        "Number of workgroups dispatched in the X dimension. Each workgroup executes the compute shader with the workgroup size defined in the shader. Total invocations in X = workgroup_count_x * workgroup_size_x.",
+        "tooltip-compute-workgroup-count-x",
         Some("#dom-gpucomputepassencoder-dispatchworkgroups"),
     );
 
     pub const WORKGROUP_COUNT_Y: TooltipInfo = TooltipInfo::new(
-        "Number of workgroups dispatched in the Y dimension. Each workgroup executes the compute shader with the workgroup size defined in the shader. Total invocations in Y = workgroup_count_y * workgroup_size_y.",
+        "tooltip-compute-workgroup-count-y",
         Some("#dom-gpucomputepassencoder-dispatchworkgroups"),
     );
 
     pub const WORKGROUP_COUNT_Z: TooltipInfo = TooltipInfo::new(
-        "Number of workgroups dispatched in the Z dimension. Each workgroup executes the compute shader with the workgroup size defined in the shader. Total invocations in Z = workgroup_count_z * workgroup_size_z.",
+        "tooltip-compute-workgroup-count-z",
         Some("#dom-gpucomputepassencoder-dispatchworkgroups"),
     );
 
     pub const ENTRY_POINT: TooltipInfo = TooltipInfo::new(
-        "Name of the compute shader function to use as the entry point. This function must be marked with @compute attribute in WGSL.",
+        "tooltip-compute-entry-point",
         Some("#dom-gpuprogrammablestage-entrypoint"),
     );
 
     pub const PIPELINE_LAYOUT: TooltipInfo = TooltipInfo::new(
-        "Defines the layout of bind groups for the compute pipeline. Can be auto-generated from the shader or manually specified to control bind group organization.",
+        "tooltip-compute-pipeline-layout",
         Some("#dom-gpucomputepipelinedescriptor-layout"),
     );
 }
@@ -572,32 +571,32 @@ pub mod load_store_op {
     use super::TooltipInfo;
 
     pub const LOAD_OP_CLEAR: TooltipInfo = TooltipInfo::new(
-        "Clear the attachment to a specified value at the start of the render pass. Discards previous contents and sets all pixels to the clear value.",
+        "tooltip-load-store-op-load-op-clear",
         Some("#dom-gpuloadop-clear"),
     );
 
     pub const LOAD_OP_LOAD: TooltipInfo = TooltipInfo::new(
-        "Load the existing contents of the attachment at the start of the render pass. Preserves previous pixel values for modification or blending.",
+        "tooltip-load-store-op-load-op-load",
         Some("#dom-gpuloadop-load"),
     );
 
     pub const STORE_OP_STORE: TooltipInfo = TooltipInfo::new(
-        "Store the results of the render pass to the attachment. Writes all rendered pixels to memory for later use.",
+        "tooltip-load-store-op-store-op-store",
         Some("#dom-gpustoreop-store"),
     );
 
     pub const STORE_OP_DISCARD: TooltipInfo = TooltipInfo::new(
-        "Discard the results of the render pass. The attachment contents become undefined after the pass. Useful for temporary rendering or when results aren't needed.",
+        "tooltip-load-store-op-store-op-discard",
         Some("#dom-gpustoreop-discard"),
     );
 
     pub const COLOR_ATTACHMENT: TooltipInfo = TooltipInfo::new(
-        "Color attachment configuration for the render pass. Specifies the texture to render into and how to handle its contents at the start and end of the pass.",
+        "tooltip-load-store-op-color-attachment",
         Some("#dictdef-gpurenderpasscolorattachment"),
     );
 
     pub const DEPTH_STENCIL_ATTACHMENT: TooltipInfo = TooltipInfo::new(
-        "Depth-stencil attachment configuration for the render pass. Specifies the depth/stencil texture and operations for depth testing and stencil operations.",
+        "tooltip-load-store-op-depth-stencil-attachment",
         Some("#dictdef-gpurenderpassdepthstencilattachment"),
     );
 }
@@ -607,22 +606,22 @@ pub mod draw {
     use super::TooltipInfo;
 
     pub const VERTEX_COUNT: TooltipInfo = TooltipInfo::new(
-        "Number of vertices to draw. For triangle lists, this is typically a multiple of 3. Each vertex is processed by the vertex shader.",
+        "tooltip-draw-vertex-count",
         Some("#dom-gpurendercommandsmixin-draw"),
     );
 
     pub const INSTANCE_COUNT: TooltipInfo = TooltipInfo::new(
-        "Number of instances to draw. Instance count greater than 1 enables instanced rendering, where the same geometry is drawn multiple times with different instance data.",
+        "tooltip-draw-instance-count",
         Some("#dom-gpurendercommandsmixin-draw"),
     );
 
     pub const FIRST_VERTEX: TooltipInfo = TooltipInfo::new(
-        "Offset of the first vertex to draw from the vertex buffer. Allows drawing a subset of vertices from a buffer without creating separate buffers.",
+        "tooltip-draw-first-vertex",
         Some("#dom-gpurendercommandsmixin-draw"),
     );
 
     pub const FIRST_INSTANCE: TooltipInfo = TooltipInfo::new(
-        "Offset of the first instance to draw. Affects the built-in instance_index value in the vertex shader, useful for accessing instance-specific data.",
+        "tooltip-draw-first-instance",
         Some("#dom-gpurendercommandsmixin-draw"),
     );
 }
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::lesson::LessonProgress;
+
 /// Represents a complete tutorial with multiple steps
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tutorial {
@@ -88,6 +90,9 @@ pub struct TutorialState {
     pub current_step: usize,
     pub completed_tutorials: Vec<String>,
     pub visited_panels: Vec<HighlightTarget>,
+    /// Progress through the interactive lessons
+    #[serde(default)]
+    pub lessons: LessonProgress,
 }
 
 impl TutorialState {
@@ -0,0 +1,669 @@
+//! Editable widgets for the uniform buffers of a shader
+//!
+//! The uniform bindings of a WGSL module are reflected with naga and every
+//! field gets a widget picked from its type and name: a slider for an
+//! `f32`, a color picker for a `vec3<f32>` or `vec4<f32>` whose name
+//! contains "color", drag values for other vectors, integers and matrix
+//! columns. WGSL does not allow `bool` in uniform buffers, so flags are
+//! `u32` or `i32` fields named like one (`use_fog`, `show_grid`,
+//! `isLit`, `shadows_enabled`) and get a checkbox.
+//!
+//! The values live in one byte buffer per binding, laid out like the
+//! shader expects, ready to be written into the bound uniform buffer.
+//! Nested struct fields are named `outer.inner` and array elements
+//! `name[i]`.
+
+use naga::proc::Layouter;
+use naga::{AddressSpace, ArraySize, Handle, Module, ScalarKind, TypeInner};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::upload_belt;
+
+/// Array elements beyond this many get no widgets and stay zero
+pub const MAX_ARRAY_ELEMENTS: u32 = 32;
+
+/// Scalar type of a field's components
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniformScalar {
+    F32,
+    I32,
+    U32,
+}
+
+/// Widget editing a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniformControl {
+    /// Slider over 0..=1 that can be typed past its ends
+    Slider,
+    /// One drag value per component
+    Drag,
+    /// Color picker, with alpha for a `vec4`
+    Color,
+    /// Checkbox writing 0 or 1
+    Checkbox,
+}
+
+/// An editable field of a uniform buffer
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformField {
+    /// Field name, `outer.inner` for fields of nested structs and
+    /// `name[i]` for array elements and matrix columns
+    pub name: String,
+    /// Byte offset from the start of the buffer
+    pub offset: usize,
+    pub scalar: UniformScalar,
+    /// Number of components, 1 to 4
+    pub components: usize,
+    pub control: UniformControl,
+}
+
+/// A uniform buffer binding and its current contents
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformBlock {
+    pub group: u32,
+    pub binding: u32,
+    /// Name of the global variable
+    pub name: String,
+    pub fields: Vec<UniformField>,
+    data: Vec<u8>,
+}
+
+impl UniformBlock {
+    /// Short description like `@group(0) @binding(1) params`
+    pub fn label(&self) -> String {
+        format!(
+            "@group({}) @binding({}) {}",
+            self.group, self.binding, self.name
+        )
+    }
+
+    /// Contents of the buffer, laid out like the shader expects
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Size of the buffer the shader binds
+    pub fn size(&self) -> u64 {
+        self.data.len() as u64
+    }
+
+    /// Field called `name`
+    pub fn field(&self, name: &str) -> Option<&UniformField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Components of a field as `f32`
+    pub fn get(&self, field: &UniformField) -> Vec<f32> {
+        (0..field.components)
+            .map(|i| {
+                let at = field.offset + i * 4;
+                let bytes: [u8; 4] = self.data[at..at + 4].try_into().unwrap();
+                match field.scalar {
+                    UniformScalar::F32 => f32::from_le_bytes(bytes),
+                    UniformScalar::I32 => i32::from_le_bytes(bytes) as f32,
+                    UniformScalar::U32 => u32::from_le_bytes(bytes) as f32,
+                }
+            })
+            .collect()
+    }
+
+    /// Set the components of a field, converting to its scalar type
+    ///
+    /// Components missing from `values` keep their value.
+    pub fn set(&mut self, field: &UniformField, values: &[f32]) {
+        for (i, &value) in values.iter().take(field.components).enumerate() {
+            let at = field.offset + i * 4;
+            let bytes = match field.scalar {
+                UniformScalar::F32 => value.to_le_bytes(),
+                UniformScalar::I32 => (value.round() as i32).to_le_bytes(),
+                UniformScalar::U32 => (value.round().max(0.0) as u32).to_le_bytes(),
+            };
+            self.data[at..at + 4].copy_from_slice(&bytes);
+        }
+    }
+
+    /// Put every field back to its starting value
+    pub fn reset(&mut self) {
+        self.data.fill(0);
+        for field in self.fields.clone() {
+            self.set(&field, &default_value(&field));
+        }
+    }
+
+    /// Write the contents into `buffer`, the buffer bound for this block,
+    /// through the upload belt
+    pub fn write(&self, queue: &wgpu::Queue, buffer: &wgpu::Buffer) {
+        upload_belt::write_buffer(queue, buffer, 0, &self.data);
+    }
+}
+
+/// Starting value of a field: sliders in the middle, colors white, matrix
+/// columns from the identity and everything else zero
+fn default_value(field: &UniformField) -> Vec<f32> {
+    match field.control {
+        UniformControl::Slider => vec![0.5],
+        UniformControl::Color => vec![1.0; field.components],
+        UniformControl::Checkbox => vec![0.0],
+        UniformControl::Drag => match matrix_column(&field.name) {
+            Some(column) => (0..field.components)
+                .map(|row| if row == column { 1.0 } else { 0.0 })
+                .collect(),
+            None => vec![0.0; field.components],
+        },
+    }
+}
+
+/// Column index of a field flattened from a matrix
+fn matrix_column(name: &str) -> Option<usize> {
+    let (_, index) = name.strip_suffix(']')?.rsplit_once("[col ")?;
+    index.parse().ok()
+}
+
+/// Whether a field name reads like an on/off flag
+pub fn is_flag_name(name: &str) -> bool {
+    let name = name.rsplit('.').next().unwrap_or(name);
+    // Words of snake_case and camelCase names
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in name.chars() {
+        if (c == '_' || c.is_uppercase()) && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c != '_' {
+            word.push(c.to_ascii_lowercase());
+        }
+    }
+    words.push(word);
+    if words.len() < 2 {
+        return false;
+    }
+    matches!(
+        words[0].as_str(),
+        "use" | "enable" | "show" | "is" | "has" | "invert" | "toggle"
+    ) || matches!(words.last().unwrap().as_str(), "enabled" | "on")
+}
+
+/// Uniform buffer bindings of a module with starting values, by group and
+/// binding
+pub fn reflect_uniforms(module: &Module) -> Result<Vec<UniformBlock>, String> {
+    let mut layouter = Layouter::default();
+    layouter
+        .update(module.to_ctx())
+        .map_err(|e| format!("Failed to compute type layouts: {}", e))?;
+
+    let mut blocks: Vec<UniformBlock> = module
+        .global_variables
+        .iter()
+        .filter(|(_, var)| var.space == AddressSpace::Uniform)
+        .filter_map(|(_, var)| {
+            let resource = var.binding.as_ref()?;
+            let name = var.name.clone().unwrap_or_default();
+            let mut fields = Vec::new();
+            // A struct's fields stand alone; a bare scalar or vector is
+            // named after the variable
+            let prefix = match module.types[var.ty].inner {
+                TypeInner::Struct { .. } => "",
+                _ => name.as_str(),
+            };
+            flatten(module, var.ty, 0, prefix, &mut fields);
+            let mut block = UniformBlock {
+                group: resource.group,
+                binding: resource.binding,
+                name,
+                fields,
+                data: vec![0; layouter[var.ty].size as usize],
+            };
+            block.reset();
+            Some(block)
+        })
+        .collect();
+    blocks.sort_by_key(|b| (b.group, b.binding));
+    Ok(blocks)
+}
+
+fn flatten(
+    module: &Module,
+    ty: Handle<naga::Type>,
+    offset: usize,
+    name: &str,
+    fields: &mut Vec<UniformField>,
+) {
+    let scalar_of = |scalar: naga::Scalar| match (scalar.kind, scalar.width) {
+        (ScalarKind::Float, 4) => Some(UniformScalar::F32),
+        (ScalarKind::Sint, 4) => Some(UniformScalar::I32),
+        (ScalarKind::Uint, 4) => Some(UniformScalar::U32),
+        // f16 and friends get no widget
+        _ => None,
+    };
+    let mut push = |name: String, scalar, components: usize, control| {
+        fields.push(UniformField {
+            name,
+            offset,
+            scalar,
+            components,
+            control,
+        })
+    };
+
+    match module.types[ty].inner {
+        TypeInner::Scalar(scalar) => {
+            let Some(scalar) = scalar_of(scalar) else {
+                return;
+            };
+            let control = match scalar {
+                UniformScalar::F32 => UniformControl::Slider,
+                _ if is_flag_name(name) => UniformControl::Checkbox,
+                _ => UniformControl::Drag,
+            };
+            push(name.to_string(), scalar, 1, control);
+        }
+        TypeInner::Vector { size, scalar } => {
+            let Some(scalar) = scalar_of(scalar) else {
+                return;
+            };
+            let components = size as usize;
+            let is_color =
+                name.to_lowercase().contains("color") || name.to_lowercase().contains("colour");
+            let control = if scalar == UniformScalar::F32 && components >= 3 && is_color {
+                UniformControl::Color
+            } else {
+                UniformControl::Drag
+            };
+            push(name.to_string(), scalar, components, control);
+        }
+        TypeInner::Matrix {
+            columns,
+            rows,
+            scalar,
+        } => {
+            let Some(scalar) = scalar_of(scalar) else {
+                return;
+            };
+            // Columns are aligned like vectors of `rows` components
+            let column_stride = if rows as usize == 2 { 8 } else { 16 };
+            for column in 0..columns as usize {
+                fields.push(UniformField {
+                    name: format!("{}[col {}]", name, column),
+                    offset: offset + column * column_stride,
+                    scalar,
+                    components: rows as usize,
+                    control: UniformControl::Drag,
+                });
+            }
+        }
+        TypeInner::Array {
+            base,
+            size: ArraySize::Constant(count),
+            stride,
+        } => {
+            for i in 0..count.get().min(MAX_ARRAY_ELEMENTS) {
+                let element = format!("{}[{}]", name, i);
+                let offset = offset + (i * stride) as usize;
+                flatten(module, base, offset, &element, fields);
+            }
+        }
+        TypeInner::Struct { ref members, .. } => {
+            for member in members {
+                let member_name = member.name.as_deref().unwrap_or("_");
+                let member_name = if name.is_empty() {
+                    member_name.to_string()
+                } else {
+                    format!("{}.{}", name, member_name)
+                };
+                let offset = offset + member.offset as usize;
+                flatten(module, member.ty, offset, &member_name, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The uniform buffers of a shader with a widget for every field
+///
+/// Reflecting a changed shader keeps the values of fields that are still
+/// there, so editing the source doesn't throw away the tweaks.
+#[derive(Debug, Clone, Default)]
+pub struct UniformTweaker {
+    blocks: Vec<UniformBlock>,
+}
+
+impl UniformTweaker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reflect the uniform buffers of a WGSL shader
+    pub fn from_wgsl(source: &str) -> Result<Self, String> {
+        let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+        let mut tweaker = Self::new();
+        tweaker.reflect(&module)?;
+        Ok(tweaker)
+    }
+
+    /// Replace the blocks with those of `module`, keeping the values of
+    /// fields with the same binding, name and type
+    pub fn reflect(&mut self, module: &Module) -> Result<(), String> {
+        let mut blocks = reflect_uniforms(module)?;
+        for block in &mut blocks {
+            let Some(old) = self
+                .blocks
+                .iter()
+                .find(|old| old.group == block.group && old.binding == block.binding)
+            else {
+                continue;
+            };
+            for field in block.fields.clone() {
+                let kept = old.fields.iter().find(|f| {
+                    f.name == field.name
+                        && f.scalar == field.scalar
+                        && f.components == field.components
+                });
+                if let Some(kept) = kept {
+                    block.set(&field, &old.get(kept));
+                }
+            }
+        }
+        self.blocks = blocks;
+        Ok(())
+    }
+
+    pub fn blocks(&self) -> &[UniformBlock] {
+        &self.blocks
+    }
+
+    /// Block bound at `group`/`binding`
+    pub fn block(&self, group: u32, binding: u32) -> Option<&UniformBlock> {
+        self.blocks
+            .iter()
+            .find(|b| b.group == group && b.binding == binding)
+    }
+
+    /// Set every field called `name`, in whichever block it is
+    ///
+    /// Returns whether a field was found.
+    pub fn set(&mut self, name: &str, values: &[f32]) -> bool {
+        let mut found = false;
+        for block in &mut self.blocks {
+            if let Some(field) = block.field(name).cloned() {
+                block.set(&field, values);
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Set one component of every field called `name`, keeping the others
+    ///
+    /// Returns whether a field with that component was found.
+    pub fn set_component(&mut self, name: &str, component: usize, value: f32) -> bool {
+        let mut found = false;
+        for block in &mut self.blocks {
+            let Some(field) = block.field(name).cloned() else {
+                continue;
+            };
+            if component < field.components {
+                let mut values = block.get(&field);
+                values[component] = value;
+                block.set(&field, &values);
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Value of the first field called `name`
+    pub fn get(&self, name: &str) -> Option<Vec<f32>> {
+        self.blocks
+            .iter()
+            .find_map(|block| block.field(name).map(|field| block.get(field)))
+    }
+
+    /// Widgets for every field; returns whether a value changed
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        self.ui_filtered(ui, |_| true)
+    }
+
+    /// Widgets for the fields of the blocks `show` accepts, leaving out
+    /// blocks the caller fills in itself
+    pub fn ui_filtered(&mut self, ui: &mut egui::Ui, show: impl Fn(&UniformBlock) -> bool) -> bool {
+        if !self.blocks.iter().any(&show) {
+            ui.label("ℹ️ The shader has no uniform buffers.");
+            return false;
+        }
+        let mut changed = false;
+        for block in self.blocks.iter_mut().filter(|block| show(block)) {
+            ui.horizontal(|ui| {
+                ui.strong(block.label());
+                ui.label(
+                    egui::RichText::new(format!("{} bytes", block.size()))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                if ui.small_button("↺ Reset").clicked() {
+                    block.reset();
+                    changed = true;
+                }
+            });
+            if block.fields.is_empty() {
+                ui.label("No editable fields");
+                continue;
+            }
+            egui::Grid::new(("uniform_tweaker", block.group, block.binding))
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for field in block.fields.clone() {
+                        ui.label(&field.name);
+                        changed |= field_ui(ui, block, &field);
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(5.0);
+        }
+        changed
+    }
+}
+
+fn field_ui(ui: &mut egui::Ui, block: &mut UniformBlock, field: &UniformField) -> bool {
+    let mut values = block.get(field);
+    let changed = match field.control {
+        UniformControl::Slider => ui
+            .add(egui::Slider::new(&mut values[0], 0.0..=1.0).clamping(egui::SliderClamping::Never))
+            .changed(),
+        UniformControl::Checkbox => {
+            let mut on = values[0] != 0.0;
+            let changed = ui.checkbox(&mut on, "").changed();
+            values[0] = if on { 1.0 } else { 0.0 };
+            changed
+        }
+        UniformControl::Color if field.components == 3 => {
+            let mut rgb = [values[0], values[1], values[2]];
+            let changed = ui.color_edit_button_rgb(&mut rgb).changed();
+            values.copy_from_slice(&rgb);
+            changed
+        }
+        UniformControl::Color => {
+            let mut rgba = [values[0], values[1], values[2], values[3]];
+            let changed = ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed();
+            values.copy_from_slice(&rgba);
+            changed
+        }
+        UniformControl::Drag => {
+            ui.horizontal(|ui| {
+                let mut changed = false;
+                for value in &mut values {
+                    let drag = match field.scalar {
+                        UniformScalar::F32 => egui::DragValue::new(value).speed(0.01),
+                        UniformScalar::I32 => {
+                            egui::DragValue::new(value).speed(0.1).fixed_decimals(0)
+                        }
+                        UniformScalar::U32 => egui::DragValue::new(value)
+                            .speed(0.1)
+                            .fixed_decimals(0)
+                            .range(0.0..=f32::MAX),
+                    };
+                    changed |= ui.add(drag).changed();
+                }
+                changed
+            })
+            .inner
+        }
+    };
+    if changed {
+        block.set(field, &values);
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADER: &str = r#"
+struct Light {
+    direction: vec3<f32>,
+    intensity: f32,
+}
+
+struct Params {
+    speed: f32,
+    base_color: vec3<f32>,
+    tint_colour: vec4<f32>,
+    offset: vec2<f32>,
+    steps: i32,
+    use_fog: u32,
+    showGrid: u32,
+    model: mat4x4<f32>,
+    lights: array<Light, 2>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(1) @binding(2) var<uniform> exposure: f32;
+@group(0) @binding(1) var<storage, read> data: array<f32>;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(params.base_color * exposure, 1.0);
+}
+"#;
+
+    fn control(block: &UniformBlock, name: &str) -> UniformControl {
+        block.field(name).unwrap().control
+    }
+
+    #[test]
+    fn test_reflects_uniform_blocks() {
+        let tweaker = UniformTweaker::from_wgsl(SHADER).unwrap();
+        let blocks = tweaker.blocks();
+        // The storage buffer has no widgets
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].label(), "@group(0) @binding(0) params");
+        assert_eq!(blocks[1].label(), "@group(1) @binding(2) exposure");
+        assert_eq!(blocks[1].fields[0].name, "exposure");
+        assert_eq!(blocks[1].size(), 4);
+
+        let params = &blocks[0];
+        assert_eq!(control(params, "speed"), UniformControl::Slider);
+        assert_eq!(control(params, "base_color"), UniformControl::Color);
+        assert_eq!(control(params, "tint_colour"), UniformControl::Color);
+        assert_eq!(control(params, "offset"), UniformControl::Drag);
+        assert_eq!(control(params, "steps"), UniformControl::Drag);
+        assert_eq!(control(params, "use_fog"), UniformControl::Checkbox);
+        assert_eq!(control(params, "showGrid"), UniformControl::Checkbox);
+        assert_eq!(
+            control(params, "lights[1].intensity"),
+            UniformControl::Slider
+        );
+    }
+
+    #[test]
+    fn test_offsets_follow_the_shader_layout() {
+        let tweaker = UniformTweaker::from_wgsl(SHADER).unwrap();
+        let params = &tweaker.blocks()[0];
+        let offset = |name| params.field(name).unwrap().offset;
+        assert_eq!(offset("speed"), 0);
+        // vec3 aligns to 16 bytes
+        assert_eq!(offset("base_color"), 16);
+        assert_eq!(offset("tint_colour"), 32);
+        assert_eq!(offset("offset"), 48);
+        assert_eq!(offset("showGrid"), 64);
+        // Matrices align to 16 bytes too
+        assert_eq!(offset("model[col 0]"), 80);
+        assert_eq!(offset("model[col 3]"), 128);
+        assert_eq!(offset("lights[0].direction"), 144);
+        assert_eq!(offset("lights[1].intensity"), 172);
+        assert_eq!(params.size(), 176);
+    }
+
+    #[test]
+    fn test_starting_values() {
+        let tweaker = UniformTweaker::from_wgsl(SHADER).unwrap();
+        assert_eq!(tweaker.get("speed"), Some(vec![0.5]));
+        assert_eq!(tweaker.get("base_color"), Some(vec![1.0; 3]));
+        assert_eq!(tweaker.get("use_fog"), Some(vec![0.0]));
+        assert_eq!(tweaker.get("model[col 2]"), Some(vec![0.0, 0.0, 1.0, 0.0]));
+        assert_eq!(tweaker.get("missing"), None);
+    }
+
+    #[test]
+    fn test_set_writes_the_bytes() {
+        let mut tweaker = UniformTweaker::from_wgsl(SHADER).unwrap();
+        assert!(tweaker.set("base_color", &[0.25, 0.5, 0.75]));
+        assert!(tweaker.set("steps", &[-3.4]));
+        assert!(tweaker.set("use_fog", &[1.0]));
+        assert!(!tweaker.set("missing", &[1.0]));
+
+        let data = tweaker.blocks()[0].data();
+        let floats: &[f32] = bytemuck::cast_slice(&data[16..28]);
+        assert_eq!(floats, [0.25, 0.5, 0.75]);
+        assert_eq!(i32::from_le_bytes(data[56..60].try_into().unwrap()), -3);
+        assert_eq!(u32::from_le_bytes(data[60..64].try_into().unwrap()), 1);
+
+        assert!(tweaker.set_component("base_color", 1, 1.0));
+        assert!(!tweaker.set_component("base_color", 3, 1.0));
+        assert_eq!(tweaker.get("base_color"), Some(vec![0.25, 1.0, 0.75]));
+    }
+
+    #[test]
+    fn test_reflect_keeps_values() {
+        let mut tweaker = UniformTweaker::from_wgsl(SHADER).unwrap();
+        tweaker.set("speed", &[3.0]);
+        tweaker.set("offset", &[1.0, 2.0]);
+
+        // `offset` becomes a vec3 and `speed` moves behind a new field
+        let edited = SHADER
+            .replace("    speed: f32,", "    scale: f32,\n    speed: f32,")
+            .replace("offset: vec2<f32>", "offset: vec3<f32>");
+        let module = naga::front::wgsl::parse_str(&edited).unwrap();
+        tweaker.reflect(&module).unwrap();
+        assert_eq!(tweaker.get("speed"), Some(vec![3.0]));
+        assert_eq!(tweaker.get("scale"), Some(vec![0.5]));
+        assert_eq!(tweaker.get("offset"), Some(vec![0.0; 3]));
+    }
+
+    #[test]
+    fn test_flag_names() {
+        for name in [
+            "use_fog",
+            "showGrid",
+            "isLit",
+            "shadows_enabled",
+            "has_normals",
+        ] {
+            assert!(is_flag_name(name), "{name}");
+        }
+        for name in ["steps", "user_count", "island", "mode", "enabled"] {
+            assert!(!is_flag_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_arrays_are_capped() {
+        let source = "struct P { weights: array<vec4<f32>, 100> }\n\
+                      @group(0) @binding(0) var<uniform> p: P;";
+        let tweaker = UniformTweaker::from_wgsl(source).unwrap();
+        let block = &tweaker.blocks()[0];
+        assert_eq!(block.fields.len(), MAX_ARRAY_ELEMENTS as usize);
+        assert_eq!(block.size(), 1600);
+    }
+}
@@ -0,0 +1,415 @@
+//! Staging belt for per-frame buffer uploads
+//!
+//! `queue.write_buffer` copies data into a staging allocation owned by wgpu
+//! on every call. The previews update uniforms and vertex data every frame,
+//! so [`UploadBelt`] keeps that staging memory instead: a ring of
+//! `MAP_WRITE` chunks that writes are sub-allocated from, with a copy into
+//! the target buffer recorded for each one. When the copies are submitted
+//! the chunks are mapped again, and the map callback, which fires once the
+//! GPU has finished reading them, hands them back for reuse. A chunk still
+//! in flight never blocks a write; the next free or a new chunk is used.
+//!
+//! The playground opens the belt for its device with
+//! [`UploadBelt::open`]. Previews write through [`write_buffer`] and submit
+//! through [`submit`], which puts the pending copies in front of their own
+//! commands. On any other device, such as one created by a test, both fall
+//! back to the plain queue calls.
+
+use std::sync::mpsc;
+use std::sync::{Mutex, OnceLock};
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+
+/// Default size of one staging chunk
+pub const DEFAULT_CHUNK_SIZE: u64 = 256 * 1024;
+
+/// Alignment of writes within a chunk, as mapped ranges require
+const WRITE_ALIGNMENT: u64 = wgpu::MAP_ALIGNMENT;
+
+/// Upload counters, for the last finished frame and since the belt opened
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UploadStats {
+    /// Bytes written through the belt in the last finished frame
+    pub frame_bytes: u64,
+    /// Writes made through the belt in the last finished frame
+    pub frame_writes: u32,
+    /// Bytes written through the belt since it opened
+    pub total_bytes: u64,
+    /// Writes made through the belt since it opened
+    pub total_writes: u64,
+    /// Writes that went straight to the queue, for another device
+    pub fallback_writes: u64,
+    /// Chunks created
+    pub chunks_allocated: u64,
+    /// Times a chunk came back from the GPU and was written again
+    pub chunks_reused: u64,
+    /// Writes made while earlier chunks were still being read by the GPU,
+    /// where a single staging buffer would have had to wait for it
+    pub stalls_avoided: u64,
+    /// Chunks submitted and not yet returned
+    pub chunks_in_flight: usize,
+    /// Chunks ready to be written
+    pub chunks_free: usize,
+    /// Total size of all chunks
+    pub staging_bytes: u64,
+}
+
+/// Offset of the next write of `size` bytes in a chunk, if it fits
+pub fn allocate(used: u64, size: u64, capacity: u64) -> Option<u64> {
+    let offset = used.next_multiple_of(WRITE_ALIGNMENT);
+    (offset + size <= capacity).then_some(offset)
+}
+
+/// Size of the chunk created for a write of `size` bytes
+pub fn chunk_size_for(size: u64, chunk_size: u64) -> u64 {
+    size.max(chunk_size).next_multiple_of(WRITE_ALIGNMENT)
+}
+
+/// A staging buffer and how much of it is written
+struct Chunk {
+    buffer: wgpu::Buffer,
+    used: u64,
+}
+
+impl Chunk {
+    fn capacity(&self) -> u64 {
+        self.buffer.size()
+    }
+}
+
+/// The belt of the open device
+struct OpenBelt {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    /// Mapped chunks being written this submission
+    active: Vec<Chunk>,
+    /// Unmapped chunks whose copies are recorded or in flight
+    closed: Vec<Chunk>,
+    /// Mapped chunks back from the GPU
+    free: Vec<Chunk>,
+    /// Chunks being mapped again, awaiting their callback
+    in_flight: usize,
+    sender: mpsc::Sender<Chunk>,
+    receiver: mpsc::Receiver<Chunk>,
+    /// Copies recorded since the last submission
+    pending: Option<wgpu::CommandEncoder>,
+    frame_bytes: u64,
+    frame_writes: u32,
+    stats: UploadStats,
+}
+
+impl OpenBelt {
+    fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            device: device.clone(),
+            queue: queue.clone(),
+            active: Vec::new(),
+            closed: Vec::new(),
+            free: Vec::new(),
+            in_flight: 0,
+            sender,
+            receiver,
+            pending: None,
+            frame_bytes: 0,
+            frame_writes: 0,
+            stats: UploadStats::default(),
+        }
+    }
+
+    /// Move chunks whose map callback has run to the free list
+    fn receive(&mut self) {
+        while let Ok(chunk) = self.receiver.try_recv() {
+            self.in_flight -= 1;
+            self.free.push(chunk);
+        }
+    }
+
+    /// A mapped chunk with room for `size` bytes and the offset to write at
+    fn chunk_for(&mut self, size: u64, chunk_size: u64) -> (usize, u64) {
+        if let Some((index, offset)) = self
+            .active
+            .iter()
+            .enumerate()
+            .find_map(|(i, chunk)| Some((i, allocate(chunk.used, size, chunk.capacity())?)))
+        {
+            return (index, offset);
+        }
+
+        self.receive();
+        if self.in_flight > 0 {
+            self.stats.stalls_avoided += 1;
+        }
+        let chunk = match self.free.iter().position(|chunk| chunk.capacity() >= size) {
+            Some(index) => {
+                self.stats.chunks_reused += 1;
+                let mut chunk = self.free.swap_remove(index);
+                chunk.used = 0;
+                chunk
+            }
+            None => {
+                let capacity = chunk_size_for(size, chunk_size);
+                ApiCoverageTracker::global().record(ApiCategory::Buffer, "create_buffer");
+                let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Upload Belt Chunk"),
+                    size: capacity,
+                    usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+                    mapped_at_creation: true,
+                });
+                self.stats.chunks_allocated += 1;
+                self.stats.staging_bytes += capacity;
+                Chunk { buffer, used: 0 }
+            }
+        };
+        self.active.push(chunk);
+        (self.active.len() - 1, 0)
+    }
+
+    fn write(&mut self, target: &wgpu::Buffer, offset: u64, data: &[u8], chunk_size: u64) {
+        let size = data.len() as u64;
+        let (index, chunk_offset) = self.chunk_for(size, chunk_size);
+        let chunk = &mut self.active[index];
+        chunk
+            .buffer
+            .slice(chunk_offset..chunk_offset + size)
+            .get_mapped_range_mut()
+            .copy_from_slice(data);
+        chunk.used = chunk_offset + size;
+
+        let tracker = ApiCoverageTracker::global();
+        let device = &self.device;
+        let encoder = self.pending.get_or_insert_with(|| {
+            tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Upload Belt Encoder"),
+            })
+        });
+        tracker.record(ApiCategory::CommandEncoder, "copy_buffer_to_buffer");
+        encoder.copy_buffer_to_buffer(
+            &self.active[index].buffer,
+            chunk_offset,
+            target,
+            offset,
+            size,
+        );
+
+        self.frame_bytes += size;
+        self.frame_writes += 1;
+        self.stats.total_bytes += size;
+        self.stats.total_writes += 1;
+    }
+
+    /// Unmap the written chunks and take the recorded copies
+    fn finish(&mut self) -> Option<wgpu::CommandBuffer> {
+        let encoder = self.pending.take()?;
+        for chunk in self.active.drain(..) {
+            chunk.buffer.unmap();
+            self.closed.push(chunk);
+        }
+        Some(encoder.finish())
+    }
+
+    /// Map the submitted chunks again; each returns once the GPU is done
+    fn recall(&mut self) {
+        let tracker = ApiCoverageTracker::global();
+        for chunk in self.closed.drain(..) {
+            let sender = self.sender.clone();
+            let buffer = chunk.buffer.clone();
+            tracker.record(ApiCategory::Buffer, "map_async");
+            buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Write, move |result| {
+                    // A chunk that fails to map, on a lost device, is dropped
+                    if result.is_ok() {
+                        let _ = sender.send(chunk);
+                    }
+                });
+            self.in_flight += 1;
+        }
+    }
+
+    fn snapshot(&self) -> UploadStats {
+        UploadStats {
+            chunks_in_flight: self.in_flight + self.closed.len(),
+            chunks_free: self.free.len(),
+            ..self.stats
+        }
+    }
+}
+
+/// Ring of staging chunks shared by the previews of one device
+pub struct UploadBelt {
+    chunk_size: u64,
+    open: Mutex<Option<OpenBelt>>,
+    /// Writes for devices the belt is not open for
+    fallback_writes: Mutex<u64>,
+}
+
+impl Default for UploadBelt {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHUNK_SIZE)
+    }
+}
+
+impl UploadBelt {
+    /// Create a closed belt that allocates chunks of `chunk_size` bytes
+    pub fn new(chunk_size: u64) -> Self {
+        Self {
+            chunk_size: chunk_size.max(WRITE_ALIGNMENT),
+            open: Mutex::new(None),
+            fallback_writes: Mutex::new(0),
+        }
+    }
+
+    /// The belt used by the whole playground
+    pub fn global() -> &'static UploadBelt {
+        static GLOBAL: OnceLock<UploadBelt> = OnceLock::new();
+        GLOBAL.get_or_init(UploadBelt::default)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<OpenBelt>> {
+        self.open.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Use the belt for uploads on `queue`
+    ///
+    /// Chunks of a previous device are dropped, as are copies not yet
+    /// submitted to it.
+    pub fn open(&self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        *self.lock() = Some(OpenBelt::new(device, queue));
+    }
+
+    /// Whether uploads on `queue` go through the belt
+    pub fn is_open_for(&self, queue: &wgpu::Queue) -> bool {
+        self.lock()
+            .as_ref()
+            .is_some_and(|open| open.queue == *queue)
+    }
+
+    /// Stage `data` for `buffer` at `offset`
+    ///
+    /// Like `queue.write_buffer`, the data must be a multiple of 4 bytes and
+    /// the buffer needs `COPY_DST`. The copy runs with the next [`submit`](Self::submit)
+    /// or [`flush`](Self::flush) on the queue.
+    pub fn write_buffer(
+        &self,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: u64,
+        data: &[u8],
+    ) {
+        if data.is_empty() {
+            return;
+        }
+        let mut open = self.lock();
+        match open.as_mut().filter(|open| open.queue == *queue) {
+            Some(open) => open.write(buffer, offset, data, self.chunk_size),
+            None => {
+                drop(open);
+                *self
+                    .fallback_writes
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner()) += 1;
+                ApiCoverageTracker::global().record(ApiCategory::Queue, "write_buffer");
+                queue.write_buffer(buffer, offset, data);
+            }
+        }
+    }
+
+    /// Submit `command_buffers`, preceded by the copies staged for the queue
+    pub fn submit<I>(&self, queue: &wgpu::Queue, command_buffers: I) -> wgpu::SubmissionIndex
+    where
+        I: IntoIterator<Item = wgpu::CommandBuffer>,
+    {
+        let mut guard = self.lock();
+        let mut open = guard.as_mut().filter(|open| open.queue == *queue);
+        let uploads = open.as_deref_mut().and_then(OpenBelt::finish);
+        ApiCoverageTracker::global().record(ApiCategory::Queue, "submit");
+        let index = queue.submit(uploads.into_iter().chain(command_buffers));
+        if let Some(open) = open {
+            open.recall();
+        }
+        index
+    }
+
+    /// Submit the staged copies on their own
+    pub fn flush(&self, queue: &wgpu::Queue) {
+        if self
+            .lock()
+            .as_ref()
+            .is_some_and(|open| open.pending.is_some())
+        {
+            self.submit(queue, std::iter::empty());
+        }
+    }
+
+    /// Close the current frame's counters
+    pub fn end_frame(&self) {
+        if let Some(open) = self.lock().as_mut() {
+            open.receive();
+            open.stats.frame_bytes = std::mem::take(&mut open.frame_bytes);
+            open.stats.frame_writes = std::mem::take(&mut open.frame_writes);
+        }
+    }
+
+    /// Current counters; all zero except fallbacks while closed
+    pub fn stats(&self) -> UploadStats {
+        let fallback_writes = *self
+            .fallback_writes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let stats = self
+            .lock()
+            .as_mut()
+            .map_or_else(UploadStats::default, |open| {
+                open.receive();
+                open.snapshot()
+            });
+        UploadStats {
+            fallback_writes,
+            ..stats
+        }
+    }
+}
+
+/// Stage `data` for `buffer` on the playground's belt, or write it through
+/// the queue if the belt is not open for it
+pub fn write_buffer(queue: &wgpu::Queue, buffer: &wgpu::Buffer, offset: u64, data: &[u8]) {
+    UploadBelt::global().write_buffer(queue, buffer, offset, data);
+}
+
+/// Submit `command_buffers` after the copies staged on the playground's belt
+pub fn submit<I>(queue: &wgpu::Queue, command_buffers: I) -> wgpu::SubmissionIndex
+where
+    I: IntoIterator<Item = wgpu::CommandBuffer>,
+{
+    UploadBelt::global().submit(queue, command_buffers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate_aligns_writes() {
+        assert_eq!(allocate(0, 64, 256), Some(0));
+        assert_eq!(allocate(4, 64, 256), Some(8));
+        assert_eq!(allocate(200, 48, 256), Some(200));
+        assert_eq!(allocate(200, 64, 256), None);
+        assert_eq!(allocate(256, 4, 256), None);
+    }
+
+    #[test]
+    fn test_chunk_size_for() {
+        assert_eq!(chunk_size_for(64, DEFAULT_CHUNK_SIZE), DEFAULT_CHUNK_SIZE);
+        // Writes larger than a chunk get a chunk of their own
+        assert_eq!(chunk_size_for(300_004, DEFAULT_CHUNK_SIZE), 300_008);
+    }
+
+    #[test]
+    fn test_closed_belt_has_no_stats() {
+        let belt = UploadBelt::new(1024);
+        belt.end_frame();
+        assert_eq!(belt.stats(), UploadStats::default());
+    }
+}
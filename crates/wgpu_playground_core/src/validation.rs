@@ -0,0 +1,610 @@
+//! Assertions about the playground state and its rendered output
+//!
+//! Exercise authors describe what a correct solution looks like as a list of
+//! [`Check`]s, e.g. "depth testing is enabled" or "the output matches the
+//! reference within 2%", and an [`ExerciseValidator`] evaluates them against
+//! the live state every frame. Lessons use the same checks; on its own, an
+//! [`Exercise`] file is loaded into the Exercise Checks panel.
+//!
+//! State assertions name a field of the serialized [`PlaygroundState`] by a
+//! dotted path, with array elements by index, e.g.
+//! `bind_group_panel.entries.0.binding_type`. Image assertions compare the
+//! latest captured frame with a reference image using the
+//! [visual regression](crate::visual_regression) difference metric.
+//!
+//! ```json
+//! {
+//!   "title": "Depth testing",
+//!   "checks": [
+//!     {
+//!       "description": "Depth testing is enabled",
+//!       "equals": { "path": "render_pipeline_panel.enable_depth_stencil", "value": true }
+//!     },
+//!     {
+//!       "description": "The output matches the reference",
+//!       "image_matches": { "reference": "depth_reference.png", "tolerance": 0.02 }
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use base64::prelude::*;
+use image::RgbaImage;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::state::PlaygroundState;
+use crate::visual_regression::external::{align_external, AlignmentSettings};
+use crate::visual_regression::image_difference;
+
+/// Prefix of references embedded in the check as a base64 PNG
+const DATA_URL_PREFIX: &str = "data:image/png;base64,";
+
+fn default_shader_path() -> String {
+    "shader_editor.source_code".to_string()
+}
+
+fn default_tolerance() -> f32 {
+    0.01
+}
+
+/// Condition on the playground state or its rendered output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Assertion {
+    /// The field equals the value
+    Equals { path: String, value: Value },
+    /// The field is a string containing the text
+    Contains { path: String, text: String },
+    /// The field is a number, or a string holding one, within the bounds
+    InRange {
+        path: String,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    /// The field is WGSL source that parses and validates
+    ShaderCompiles {
+        #[serde(default = "default_shader_path")]
+        path: String,
+    },
+    /// The rendered output matches a reference image
+    ImageMatches {
+        /// Path of a PNG file, or a `data:image/png;base64,` URL
+        reference: String,
+        /// Largest mean difference allowed (0.0 - 1.0)
+        #[serde(default = "default_tolerance")]
+        tolerance: f32,
+        /// Part of the output compared, as `[x, y, width, height]` in pixels;
+        /// the reference is scaled to its size
+        #[serde(default)]
+        region: Option<[u32; 4]>,
+    },
+}
+
+/// Assertion with the text shown to the learner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Check {
+    /// What the check asks for, e.g. "Depth testing is enabled"
+    pub description: String,
+    #[serde(flatten)]
+    pub assertion: Assertion,
+    /// Shown while the check fails
+    #[serde(default)]
+    pub hint: Option<String>,
+}
+
+/// Result of evaluating a check
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Passed,
+    /// The assertion does not hold, with what was found instead
+    Failed(String),
+    /// The assertion needs something not available yet, such as a captured
+    /// frame
+    Pending(String),
+}
+
+impl Outcome {
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Outcome::Passed)
+    }
+}
+
+/// Named list of checks, loaded from a JSON file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exercise {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub checks: Vec<Check>,
+}
+
+impl Exercise {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Load an exercise file
+    ///
+    /// Relative reference image paths are resolved against the directory of
+    /// the file.
+    pub fn load_from_file(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        let mut exercise = Self::from_json(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for check in &mut exercise.checks {
+            if let Assertion::ImageMatches { reference, .. } = &mut check.assertion {
+                if !reference.starts_with(DATA_URL_PREFIX) && Path::new(reference).is_relative() {
+                    *reference = dir.join(&*reference).to_string_lossy().into_owned();
+                }
+            }
+        }
+        Ok(exercise)
+    }
+}
+
+/// Field of a serialized state at a dotted path, if it exists
+pub fn field_at<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+impl Assertion {
+    /// Path of the state field the assertion reads, if it reads one
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Assertion::Equals { path, .. }
+            | Assertion::Contains { path, .. }
+            | Assertion::InRange { path, .. }
+            | Assertion::ShaderCompiles { path } => Some(path),
+            Assertion::ImageMatches { .. } => None,
+        }
+    }
+}
+
+/// Evaluates checks, caching decoded reference images, shader validation
+/// and image comparisons between frames
+#[derive(Default)]
+pub struct ExerciseValidator {
+    /// Latest captured frame
+    rendered: Option<RgbaImage>,
+    /// Decoded reference images by their `reference` string
+    references: HashMap<String, Result<RgbaImage, String>>,
+    /// Image outcomes against the current frame, by reference, tolerance and
+    /// region
+    image_outcomes: HashMap<(String, u32, Option<[u32; 4]>), Outcome>,
+    /// Last shader source validated at each path and its outcome
+    shader_outcomes: HashMap<String, (String, Outcome)>,
+}
+
+impl ExerciseValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a captured frame as the rendered output
+    pub fn set_rendered(&mut self, image: RgbaImage) {
+        self.rendered = Some(image);
+        self.image_outcomes.clear();
+    }
+
+    pub fn has_rendered(&self) -> bool {
+        self.rendered.is_some()
+    }
+
+    /// Evaluate checks against a state, in order
+    pub fn evaluate(&mut self, checks: &[Check], state: &PlaygroundState) -> Vec<Outcome> {
+        let state = serde_json::to_value(state).unwrap_or(Value::Null);
+        checks
+            .iter()
+            .map(|check| self.evaluate_assertion(&check.assertion, &state))
+            .collect()
+    }
+
+    /// Evaluate one assertion against a serialized state
+    pub fn evaluate_assertion(&mut self, assertion: &Assertion, state: &Value) -> Outcome {
+        if let Assertion::ImageMatches {
+            reference,
+            tolerance,
+            region,
+        } = assertion
+        {
+            return self.evaluate_image(reference, *tolerance, *region);
+        }
+
+        let path = assertion.path().unwrap_or_default();
+        let Some(field) = field_at(state, path) else {
+            return Outcome::Failed(format!("{} is not set", path));
+        };
+        match assertion {
+            Assertion::Equals { value, .. } if field == value => Outcome::Passed,
+            Assertion::Equals { .. } => Outcome::Failed(format!("{} is {}", path, field)),
+            Assertion::Contains { text, .. } => match field.as_str() {
+                Some(s) if s.contains(text.as_str()) => Outcome::Passed,
+                Some(_) => Outcome::Failed(format!("{} does not contain {:?}", path, text)),
+                None => Outcome::Failed(format!("{} is not text", path)),
+            },
+            Assertion::InRange { min, max, .. } => {
+                let number = match field {
+                    Value::String(s) => s.trim().parse::<f64>().ok(),
+                    field => field.as_f64(),
+                };
+                match number {
+                    None => Outcome::Failed(format!("{} is not a number", path)),
+                    Some(n) if min.is_some_and(|min| n < min) || max.is_some_and(|max| n > max) => {
+                        Outcome::Failed(format!("{} is {}", path, n))
+                    }
+                    Some(_) => Outcome::Passed,
+                }
+            }
+            Assertion::ShaderCompiles { .. } => {
+                let Some(source) = field.as_str() else {
+                    return Outcome::Failed(format!("{} is not text", path));
+                };
+                if let Some((validated, outcome)) = self.shader_outcomes.get(path) {
+                    if validated == source {
+                        return outcome.clone();
+                    }
+                }
+                let outcome = match validate_wgsl(source) {
+                    Ok(()) => Outcome::Passed,
+                    Err(e) => Outcome::Failed(e),
+                };
+                self.shader_outcomes
+                    .insert(path.to_string(), (source.to_string(), outcome.clone()));
+                outcome
+            }
+            Assertion::ImageMatches { .. } => unreachable!("handled above"),
+        }
+    }
+
+    fn evaluate_image(
+        &mut self,
+        reference: &str,
+        tolerance: f32,
+        region: Option<[u32; 4]>,
+    ) -> Outcome {
+        let Some(rendered) = &self.rendered else {
+            return Outcome::Pending("No frame has been captured yet".to_string());
+        };
+        let key = (reference.to_string(), tolerance.to_bits(), region);
+        if let Some(outcome) = self.image_outcomes.get(&key) {
+            return outcome.clone();
+        }
+
+        let reference_image = self
+            .references
+            .entry(reference.to_string())
+            .or_insert_with(|| load_reference(reference));
+        let outcome = match reference_image {
+            Ok(reference_image) => compare_output(rendered, reference_image, tolerance, region),
+            Err(e) => Outcome::Failed(e.clone()),
+        };
+        self.image_outcomes.insert(key, outcome.clone());
+        outcome
+    }
+}
+
+/// Parse and validate WGSL, returning the first error line
+fn validate_wgsl(source: &str) -> Result<(), String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.message().to_string())?;
+    Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|e| e.as_inner().to_string())?;
+    Ok(())
+}
+
+/// Decode a reference from a data URL or load it from a file
+fn load_reference(reference: &str) -> Result<RgbaImage, String> {
+    let image = match reference.strip_prefix(DATA_URL_PREFIX) {
+        Some(data) => {
+            let bytes = BASE64_STANDARD
+                .decode(data.trim())
+                .map_err(|e| format!("Invalid embedded reference: {}", e))?;
+            image::load_from_memory(&bytes)
+                .map_err(|e| format!("Invalid embedded reference: {}", e))?
+        }
+        None => image::open(reference).map_err(|e| format!("Cannot load {}: {}", reference, e))?,
+    };
+    Ok(image.to_rgba8())
+}
+
+/// Compare a region of the output with a reference scaled to its size
+fn compare_output(
+    rendered: &RgbaImage,
+    reference: &RgbaImage,
+    tolerance: f32,
+    region: Option<[u32; 4]>,
+) -> Outcome {
+    let output = match region {
+        Some([x, y, width, height]) => {
+            if x.saturating_add(width) > rendered.width()
+                || y.saturating_add(height) > rendered.height()
+                || width == 0
+                || height == 0
+            {
+                return Outcome::Failed(format!(
+                    "The region {:?} is outside the {}x{} output",
+                    [x, y, width, height],
+                    rendered.width(),
+                    rendered.height()
+                ));
+            }
+            image::imageops::crop_imm(rendered, x, y, width, height).to_image()
+        }
+        None => rendered.clone(),
+    };
+    let aligned = align_external(
+        reference,
+        output.dimensions(),
+        &AlignmentSettings::default(),
+    );
+    match image_difference(&output, &aligned) {
+        Ok(difference) if difference <= tolerance => Outcome::Passed,
+        Ok(difference) => Outcome::Failed(format!(
+            "The output differs by {:.1}% (up to {:.1}% allowed)",
+            difference * 100.0,
+            tolerance * 100.0
+        )),
+        Err(e) => Outcome::Failed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn evaluate(assertion: Assertion, state: &Value) -> Outcome {
+        ExerciseValidator::new().evaluate_assertion(&assertion, state)
+    }
+
+    fn png_data_url(image: &RgbaImage) -> String {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut bytes, image::ImageFormat::Png).unwrap();
+        format!(
+            "{}{}",
+            DATA_URL_PREFIX,
+            BASE64_STANDARD.encode(bytes.into_inner())
+        )
+    }
+
+    #[test]
+    fn test_field_at_follows_objects_and_arrays() {
+        let value = serde_json::json!({
+            "panel": { "entries": [{ "binding": 3 }], "label": "group" }
+        });
+        assert_eq!(field_at(&value, "panel.label"), Some(&Value::from("group")));
+        assert_eq!(
+            field_at(&value, "panel.entries.0.binding"),
+            Some(&Value::from(3))
+        );
+        assert_eq!(field_at(&value, "panel.entries.1.binding"), None);
+        assert_eq!(field_at(&value, "panel.label.length"), None);
+    }
+
+    #[test]
+    fn test_state_assertions() {
+        let state =
+            serde_json::json!({ "shader": "@fragment fn main()", "depth": true, "size": "4096" });
+        let path = || "depth".to_string();
+        assert!(evaluate(
+            Assertion::Equals {
+                path: path(),
+                value: true.into()
+            },
+            &state
+        )
+        .is_passed());
+        assert_eq!(
+            evaluate(
+                Assertion::Equals {
+                    path: path(),
+                    value: false.into()
+                },
+                &state
+            ),
+            Outcome::Failed("depth is true".to_string())
+        );
+        assert!(evaluate(
+            Assertion::Contains {
+                path: "shader".into(),
+                text: "@fragment".into()
+            },
+            &state
+        )
+        .is_passed());
+        assert!(!evaluate(
+            Assertion::Contains {
+                path: path(),
+                text: "true".into()
+            },
+            &state
+        )
+        .is_passed());
+        assert_eq!(
+            evaluate(
+                Assertion::Equals {
+                    path: "missing".into(),
+                    value: Value::Null
+                },
+                &state
+            ),
+            Outcome::Failed("missing is not set".to_string())
+        );
+    }
+
+    #[test]
+    fn test_in_range_reads_numbers_in_strings() {
+        let state = serde_json::json!({ "size": "4096", "count": 3 });
+        let in_range = |path: &str, min, max| Assertion::InRange {
+            path: path.to_string(),
+            min,
+            max,
+        };
+        assert!(evaluate(in_range("size", Some(1024.0), None), &state).is_passed());
+        assert!(!evaluate(in_range("size", None, Some(1024.0)), &state).is_passed());
+        assert!(evaluate(in_range("count", Some(3.0), Some(3.0)), &state).is_passed());
+        assert_eq!(
+            evaluate(in_range("count", Some(4.0), None), &state),
+            Outcome::Failed("count is 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_shader_compiles() {
+        let valid = "@compute @workgroup_size(1) fn main() {}";
+        let state = serde_json::json!({
+            "shader_editor": { "source_code": valid },
+            "broken": "fn main( {}"
+        });
+        let mut validator = ExerciseValidator::new();
+        let default_path: Assertion =
+            serde_json::from_value(serde_json::json!({ "shader_compiles": {} })).unwrap();
+        assert!(validator
+            .evaluate_assertion(&default_path, &state)
+            .is_passed());
+        // Served from the cache the second time
+        assert!(validator
+            .evaluate_assertion(&default_path, &state)
+            .is_passed());
+        assert!(!validator
+            .evaluate_assertion(
+                &Assertion::ShaderCompiles {
+                    path: "broken".into()
+                },
+                &state
+            )
+            .is_passed());
+    }
+
+    #[test]
+    fn test_image_matches_reference_within_tolerance() {
+        let reference = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+        let assertion = |tolerance| Assertion::ImageMatches {
+            reference: png_data_url(&reference),
+            tolerance,
+            region: None,
+        };
+        let mut validator = ExerciseValidator::new();
+        assert!(matches!(
+            validator.evaluate_assertion(&assertion(0.02), &Value::Null),
+            Outcome::Pending(_)
+        ));
+
+        // Twice the size of the reference, with a quarter of it blue
+        let mut rendered = RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255]));
+        for y in 0..4 {
+            for x in 0..4 {
+                rendered.put_pixel(x, y, Rgba([0, 0, 255, 255]));
+            }
+        }
+        validator.set_rendered(rendered);
+        assert!(!validator
+            .evaluate_assertion(&assertion(0.02), &Value::Null)
+            .is_passed());
+        assert!(validator
+            .evaluate_assertion(&assertion(0.2), &Value::Null)
+            .is_passed());
+
+        let region = Assertion::ImageMatches {
+            reference: png_data_url(&reference),
+            tolerance: 0.0,
+            region: Some([4, 4, 4, 4]),
+        };
+        assert!(validator
+            .evaluate_assertion(&region, &Value::Null)
+            .is_passed());
+        let outside = Assertion::ImageMatches {
+            reference: png_data_url(&reference),
+            tolerance: 0.0,
+            region: Some([6, 6, 4, 4]),
+        };
+        assert!(!validator
+            .evaluate_assertion(&outside, &Value::Null)
+            .is_passed());
+    }
+
+    #[test]
+    fn test_missing_reference_fails() {
+        let mut validator = ExerciseValidator::new();
+        validator.set_rendered(RgbaImage::new(2, 2));
+        let outcome = validator.evaluate_assertion(
+            &Assertion::ImageMatches {
+                reference: "/nonexistent/reference.png".into(),
+                tolerance: 0.01,
+                region: None,
+            },
+            &Value::Null,
+        );
+        assert!(matches!(outcome, Outcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_checks_parse_from_json() {
+        let exercise = Exercise::from_json(
+            r#"{
+                "title": "Depth",
+                "checks": [
+                    {
+                        "description": "Depth testing is enabled",
+                        "equals": { "path": "render_pipeline_panel.enable_depth_stencil", "value": true },
+                        "hint": "Tick Enable Depth-Stencil Testing"
+                    },
+                    {
+                        "description": "Matches",
+                        "image_matches": { "reference": "depth.png" }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(exercise.checks.len(), 2);
+        assert_eq!(
+            exercise.checks[0].hint.as_deref(),
+            Some("Tick Enable Depth-Stencil Testing")
+        );
+        assert_eq!(
+            exercise.checks[1].assertion,
+            Assertion::ImageMatches {
+                reference: "depth.png".into(),
+                tolerance: 0.01,
+                region: None
+            }
+        );
+
+        let state = PlaygroundState::new();
+        let outcomes = ExerciseValidator::new().evaluate(&exercise.checks, &state);
+        assert!(matches!(outcomes[0], Outcome::Failed(_)));
+        assert!(matches!(outcomes[1], Outcome::Pending(_)));
+    }
+
+    #[test]
+    fn test_relative_references_resolve_against_the_exercise_file() {
+        let dir = std::env::temp_dir().join("wgpu_test_exercise_references");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("exercise.json");
+        std::fs::write(
+            &path,
+            r#"{"title": "T", "checks": [{"description": "D", "image_matches": {"reference": "ref.png"}}]}"#,
+        )
+        .unwrap();
+
+        let exercise = Exercise::load_from_file(&path).unwrap();
+        let Assertion::ImageMatches { reference, .. } = &exercise.checks[0].assertion else {
+            panic!("Expected an image assertion");
+        };
+        assert_eq!(Path::new(reference), dir.join("ref.png"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
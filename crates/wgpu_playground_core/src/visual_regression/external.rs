@@ -0,0 +1,365 @@
+//! Comparison against images produced by other implementations
+//!
+//! Screenshots from a native D3D/Vulkan sample or a browser rarely line up
+//! with the playground's output pixel for pixel: they include window chrome,
+//! have a different size or are shifted by a few pixels. [`align_external`]
+//! crops, rescales and offsets the external image according to
+//! [`AlignmentSettings`] and [`compare_external`] then applies the same
+//! difference metric as [`compare_with_reference`](super::compare_with_reference),
+//! restricted to the pixels both images cover.
+
+use super::{ComparisonResult, VisualRegressionError};
+use image::{imageops, Rgba, RgbaImage};
+use std::path::Path;
+
+/// Region of the external image to keep, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl CropRect {
+    /// Clamp the rectangle to an image of the given size
+    pub fn clamped(&self, width: u32, height: u32) -> CropRect {
+        let x = self.x.min(width.saturating_sub(1));
+        let y = self.y.min(height.saturating_sub(1));
+        CropRect {
+            x,
+            y,
+            width: self.width.clamp(1, width - x),
+            height: self.height.clamp(1, height - y),
+        }
+    }
+}
+
+/// How the external image is mapped onto the playground image
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignmentSettings {
+    /// Crop applied to the external image first
+    pub crop: Option<CropRect>,
+    /// Rescale the cropped image to the playground image size
+    pub resize_to_match: bool,
+    /// Shift of the external image relative to the playground image
+    pub offset: (i32, i32),
+}
+
+impl Default for AlignmentSettings {
+    fn default() -> Self {
+        Self {
+            crop: None,
+            resize_to_match: true,
+            offset: (0, 0),
+        }
+    }
+}
+
+/// Metrics of an external comparison
+#[derive(Debug, Clone)]
+pub struct ExternalComparison {
+    /// Mean per-channel difference over the compared pixels (0.0 - 1.0)
+    pub difference: f32,
+    /// Largest per-channel difference (0 - 255)
+    pub max_channel_difference: u8,
+    /// Peak signal-to-noise ratio in dB, infinite for identical images
+    pub psnr: f32,
+    /// Pixels whose difference exceeds the per-pixel tolerance
+    pub mismatched_pixels: u64,
+    /// Pixels covered by both images
+    pub compared_pixels: u64,
+    /// Difference visualization in playground image coordinates; red marks
+    /// differences, blue marks pixels the external image does not cover
+    pub diff_image: RgbaImage,
+}
+
+impl ExternalComparison {
+    /// Fraction of compared pixels above the per-pixel tolerance
+    pub fn mismatched_fraction(&self) -> f32 {
+        if self.compared_pixels == 0 {
+            return 1.0;
+        }
+        self.mismatched_pixels as f32 / self.compared_pixels as f32
+    }
+
+    /// Fraction of the playground image covered by the external image
+    pub fn coverage(&self) -> f32 {
+        let total = self.diff_image.width() as u64 * self.diff_image.height() as u64;
+        if total == 0 {
+            return 0.0;
+        }
+        self.compared_pixels as f32 / total as f32
+    }
+
+    /// Convert to a [`ComparisonResult`] using a difference threshold
+    pub fn to_comparison_result(&self, threshold: f32) -> ComparisonResult {
+        ComparisonResult {
+            is_match: self.compared_pixels > 0 && self.difference <= threshold,
+            difference: self.difference,
+            diff_image_path: None,
+        }
+    }
+}
+
+/// Load an external image of any format supported by the `image` crate
+pub fn load_external_image(path: &Path) -> Result<RgbaImage, VisualRegressionError> {
+    image::open(path)
+        .map(|image| image.to_rgba8())
+        .map_err(|e| {
+            VisualRegressionError::ReferenceLoadError(format!(
+                "Failed to load {}: {}",
+                path.display(),
+                e
+            ))
+        })
+}
+
+/// Apply crop and rescale to the external image
+///
+/// The offset is not applied here; it is taken into account when comparing.
+pub fn align_external(
+    external: &RgbaImage,
+    target_size: (u32, u32),
+    settings: &AlignmentSettings,
+) -> RgbaImage {
+    let cropped = match settings.crop {
+        Some(crop) => {
+            let crop = crop.clamped(external.width(), external.height());
+            imageops::crop_imm(external, crop.x, crop.y, crop.width, crop.height).to_image()
+        }
+        None => external.clone(),
+    };
+
+    if settings.resize_to_match && cropped.dimensions() != target_size {
+        imageops::resize(
+            &cropped,
+            target_size.0.max(1),
+            target_size.1.max(1),
+            imageops::FilterType::Triangle,
+        )
+    } else {
+        cropped
+    }
+}
+
+/// Compare the playground image with an aligned external image
+///
+/// `offset` shifts the external image; only overlapping pixels are compared.
+/// A pixel counts as mismatched if its mean channel difference is above
+/// `pixel_tolerance` (0.0 - 1.0). With `ignore_alpha`, alpha is treated as
+/// opaque in both images, which suits screenshots of presented frames.
+pub fn compare_external(
+    playground: &RgbaImage,
+    aligned_external: &RgbaImage,
+    offset: (i32, i32),
+    pixel_tolerance: f32,
+    ignore_alpha: bool,
+) -> ExternalComparison {
+    let (width, height) = playground.dimensions();
+    let mut diff_image = RgbaImage::from_pixel(width, height, Rgba([0, 0, 64, 255]));
+    let mut total_diff = 0.0f64;
+    let mut squared_error = 0.0f64;
+    let mut max_channel_difference = 0u8;
+    let mut mismatched_pixels = 0u64;
+    let mut compared_pixels = 0u64;
+    let channels = if ignore_alpha { 3 } else { 4 };
+
+    for y in 0..height {
+        for x in 0..width {
+            let Some(external) =
+                pixel_at(aligned_external, x as i32 - offset.0, y as i32 - offset.1)
+            else {
+                continue;
+            };
+            let ours = playground.get_pixel(x, y);
+
+            let mut pixel_diff = 0.0f32;
+            for c in 0..channels {
+                let d = ours[c].abs_diff(external[c]);
+                max_channel_difference = max_channel_difference.max(d);
+                pixel_diff += d as f32 / 255.0;
+                squared_error += (d as f64) * (d as f64);
+            }
+            pixel_diff /= channels as f32;
+
+            total_diff += pixel_diff as f64;
+            compared_pixels += 1;
+            if pixel_diff > pixel_tolerance {
+                mismatched_pixels += 1;
+            }
+            let intensity = (pixel_diff * 255.0) as u8;
+            diff_image.put_pixel(x, y, Rgba([intensity, 0, 0, 255]));
+        }
+    }
+
+    let difference = if compared_pixels == 0 {
+        1.0
+    } else {
+        (total_diff / compared_pixels as f64) as f32
+    };
+    let mse = if compared_pixels == 0 {
+        f64::MAX
+    } else {
+        squared_error / (compared_pixels * channels as u64) as f64
+    };
+    let psnr = if mse == 0.0 {
+        f32::INFINITY
+    } else {
+        (10.0 * (255.0f64 * 255.0 / mse).log10()) as f32
+    };
+
+    ExternalComparison {
+        difference,
+        max_channel_difference,
+        psnr,
+        mismatched_pixels,
+        compared_pixels,
+        diff_image,
+    }
+}
+
+fn pixel_at(image: &RgbaImage, x: i32, y: i32) -> Option<&Rgba<u8>> {
+    if x < 0 || y < 0 || x as u32 >= image.width() || y as u32 >= image.height() {
+        return None;
+    }
+    Some(image.get_pixel(x as u32, y as u32))
+}
+
+/// Find the offset within `±max_shift` pixels that best aligns the images
+///
+/// Uses the mean luminance difference over a sparse grid of pixels, ignoring
+/// offsets where less than half of the playground image would be covered.
+pub fn auto_align(
+    playground: &RgbaImage,
+    aligned_external: &RgbaImage,
+    max_shift: u32,
+) -> (i32, i32) {
+    let (width, height) = playground.dimensions();
+    let step = ((width.max(height) / 128).max(1)) as usize;
+    let luma = |p: &Rgba<u8>| 0.299 * p[0] as f32 + 0.587 * p[1] as f32 + 0.114 * p[2] as f32;
+    let max_shift = max_shift as i32;
+
+    let mut best: (i32, i32) = (0, 0);
+    let mut best_score = f32::MAX;
+    for dy in -max_shift..=max_shift {
+        for dx in -max_shift..=max_shift {
+            let mut sum = 0.0f32;
+            let mut count = 0u32;
+            let mut samples = 0u32;
+            for y in (0..height).step_by(step) {
+                for x in (0..width).step_by(step) {
+                    samples += 1;
+                    if let Some(external) = pixel_at(aligned_external, x as i32 - dx, y as i32 - dy)
+                    {
+                        sum += (luma(playground.get_pixel(x, y)) - luma(external)).abs();
+                        count += 1;
+                    }
+                }
+            }
+            if count * 2 < samples {
+                continue;
+            }
+            let score = sum / count as f32;
+            // Prefer the smallest shift among equally good candidates
+            let closer = dx.abs() + dy.abs() < best.0.abs() + best.1.abs();
+            if score < best_score || (score == best_score && closer) {
+                best_score = score;
+                best = (dx, dy);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Image with a bright square at (`x`, `y`) on a dark background
+    fn square_image(width: u32, height: u32, x: u32, y: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |px, py| {
+            if (x..x + 8).contains(&px) && (y..y + 8).contains(&py) {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([20, 20, 20, 255])
+            }
+        })
+    }
+
+    #[test]
+    fn test_crop_is_clamped() {
+        let crop = CropRect {
+            x: 30,
+            y: 5,
+            width: 100,
+            height: 0,
+        };
+        assert_eq!(
+            crop.clamped(32, 32),
+            CropRect {
+                x: 30,
+                y: 5,
+                width: 2,
+                height: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_align_crops_and_resizes() {
+        // External screenshot with a 4 pixel border of window chrome
+        let mut external = RgbaImage::from_pixel(40, 40, Rgba([0, 0, 255, 255]));
+        imageops::replace(&mut external, &square_image(32, 32, 8, 8), 4, 4);
+
+        let settings = AlignmentSettings {
+            crop: Some(CropRect {
+                x: 4,
+                y: 4,
+                width: 32,
+                height: 32,
+            }),
+            ..Default::default()
+        };
+        let aligned = align_external(&external, (32, 32), &settings);
+        assert_eq!(aligned, square_image(32, 32, 8, 8));
+
+        let resized = align_external(&external, (20, 10), &AlignmentSettings::default());
+        assert_eq!(resized.dimensions(), (20, 10));
+    }
+
+    #[test]
+    fn test_identical_images() {
+        let image = square_image(32, 32, 4, 4);
+        let result = compare_external(&image, &image, (0, 0), 0.0, false);
+        assert_eq!(result.difference, 0.0);
+        assert_eq!(result.mismatched_pixels, 0);
+        assert_eq!(result.compared_pixels, 32 * 32);
+        assert!(result.psnr.is_infinite());
+        assert!(result.to_comparison_result(0.01).is_match);
+    }
+
+    #[test]
+    fn test_offset_restricts_comparison_to_overlap() {
+        let playground = square_image(32, 32, 12, 10);
+        let external = square_image(32, 32, 10, 10);
+
+        let misaligned = compare_external(&playground, &external, (0, 0), 0.1, true);
+        assert!(misaligned.mismatched_pixels > 0);
+        assert_eq!(misaligned.max_channel_difference, 235);
+
+        let aligned = compare_external(&playground, &external, (2, 0), 0.1, true);
+        assert_eq!(aligned.difference, 0.0);
+        assert_eq!(aligned.compared_pixels, 30 * 32);
+        assert!(aligned.coverage() < 1.0);
+        // Uncovered columns are marked blue
+        assert_eq!(aligned.diff_image.get_pixel(0, 0), &Rgba([0, 0, 64, 255]));
+    }
+
+    #[test]
+    fn test_auto_align_finds_shift() {
+        let playground = square_image(48, 48, 20, 18);
+        let external = square_image(48, 48, 17, 20);
+        assert_eq!(auto_align(&playground, &external, 5), (3, -2));
+    }
+}
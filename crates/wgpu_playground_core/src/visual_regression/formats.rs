@@ -0,0 +1,313 @@
+//! Conversion of captured texel data to 8-bit RGBA images
+//!
+//! [`capture_texture`](super::capture_texture) copies a texture into a buffer
+//! in its native format. The helpers here decide how many bytes each texel
+//! takes and turn the raw data into an [`RgbaImage`] that can be compared
+//! against a PNG reference. Float and depth formats are mapped to 0-255 using
+//! a [`Normalization`] range. Other color formats with 4-byte texels, such as
+//! `Rgba8Snorm` or `Rgb10a2Unorm`, are passed through as raw RGBA bytes.
+
+use super::VisualRegressionError;
+use image::RgbaImage;
+use wgpu::{TextureAspect, TextureFormat};
+
+/// How float and depth values are mapped to the 0-255 range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalization {
+    /// Map `min..=max` to `0..=255`, clamping values outside the range
+    Range { min: f32, max: f32 },
+    /// Use the smallest and largest finite value in the captured image
+    Auto,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self::Range { min: 0.0, max: 1.0 }
+    }
+}
+
+/// Options for [`capture_texture_with_options`](super::capture_texture_with_options)
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOptions {
+    /// Normalization applied to float and depth formats
+    pub normalization: Normalization,
+}
+
+/// Formats that are decoded when captured
+///
+/// See [`is_raw_format`] for the formats captured as raw bytes.
+pub const SUPPORTED_FORMATS: &[TextureFormat] = &[
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::Rgba16Float,
+    TextureFormat::R32Float,
+    TextureFormat::Depth16Unorm,
+    TextureFormat::Depth32Float,
+    TextureFormat::Depth32FloatStencil8,
+];
+
+/// Aspect copied when capturing a texture of the given format
+///
+/// Combined depth-stencil formats only have their depth aspect captured.
+pub fn capture_aspect(format: TextureFormat) -> TextureAspect {
+    if format.is_combined_depth_stencil_format() {
+        TextureAspect::DepthOnly
+    } else {
+        TextureAspect::All
+    }
+}
+
+/// Whether the format is captured by copying its 4-byte texels as RGBA bytes
+///
+/// This covers uncompressed color formats that are not decoded, such as
+/// `Rgba8Snorm`, `Rgba8Uint`, `Rgb10a2Unorm` or `Rg16Float`.
+pub fn is_raw_format(format: TextureFormat) -> bool {
+    !SUPPORTED_FORMATS.contains(&format)
+        && !format.is_depth_stencil_format()
+        && !format.is_compressed()
+        && format.block_copy_size(None) == Some(4)
+}
+
+/// Bytes per texel of the captured aspect, or `None` if the format is unsupported
+pub fn bytes_per_pixel(format: TextureFormat) -> Option<u32> {
+    if is_raw_format(format) {
+        return Some(4);
+    }
+    if !SUPPORTED_FORMATS.contains(&format) {
+        return None;
+    }
+    format.block_copy_size(Some(capture_aspect(format)))
+}
+
+/// Convert tightly packed texel data to an 8-bit RGBA image
+///
+/// `data` must contain `width * height` texels of the captured aspect of
+/// `format` without row padding.
+pub fn convert_to_rgba8(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    format: TextureFormat,
+    normalization: Normalization,
+) -> Result<RgbaImage, VisualRegressionError> {
+    let bpp = bytes_per_pixel(format).ok_or_else(|| {
+        VisualRegressionError::CaptureError(format!("Unsupported capture format {:?}", format))
+    })? as usize;
+    let texel_count = (width * height) as usize;
+    if data.len() < texel_count * bpp {
+        return Err(VisualRegressionError::CaptureError(format!(
+            "Expected {} bytes of texel data, got {}",
+            texel_count * bpp,
+            data.len()
+        )));
+    }
+    let texels = data[..texel_count * bpp].chunks_exact(bpp);
+
+    let pixels: Vec<u8> = match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+            data[..texel_count * 4].to_vec()
+        }
+        _ if is_raw_format(format) => data[..texel_count * 4].to_vec(),
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+            texels.flat_map(|t| [t[2], t[1], t[0], t[3]]).collect()
+        }
+        TextureFormat::Rgba16Float => {
+            let values: Vec<[f32; 4]> = texels
+                .map(|t| {
+                    let channel =
+                        |i: usize| f16_to_f32(u16::from_le_bytes([t[i * 2], t[i * 2 + 1]]));
+                    [channel(0), channel(1), channel(2), channel(3)]
+                })
+                .collect();
+            let (min, max) = resolve_range(
+                normalization,
+                values.iter().flat_map(|v| v[..3].iter().copied()),
+            );
+            values
+                .iter()
+                .flat_map(|v| {
+                    [
+                        normalize(v[0], min, max),
+                        normalize(v[1], min, max),
+                        normalize(v[2], min, max),
+                        normalize(v[3], 0.0, 1.0),
+                    ]
+                })
+                .collect()
+        }
+        _ => {
+            // Single-channel formats are shown as grayscale
+            let values: Vec<f32> = texels
+                .map(|t| match format {
+                    TextureFormat::Depth16Unorm => {
+                        u16::from_le_bytes([t[0], t[1]]) as f32 / u16::MAX as f32
+                    }
+                    _ => f32::from_le_bytes([t[0], t[1], t[2], t[3]]),
+                })
+                .collect();
+            let (min, max) = resolve_range(normalization, values.iter().copied());
+            values
+                .iter()
+                .flat_map(|&v| {
+                    let gray = normalize(v, min, max);
+                    [gray, gray, gray, 255]
+                })
+                .collect()
+        }
+    };
+
+    RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| VisualRegressionError::CaptureError("Failed to create image buffer".into()))
+}
+
+fn resolve_range(normalization: Normalization, values: impl Iterator<Item = f32>) -> (f32, f32) {
+    match normalization {
+        Normalization::Range { min, max } => (min, max),
+        Normalization::Auto => values
+            .filter(|v| v.is_finite())
+            .fold(None, |range: Option<(f32, f32)>, v| match range {
+                Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+                None => Some((v, v)),
+            })
+            .unwrap_or((0.0, 1.0)),
+    }
+}
+
+fn normalize(value: f32, min: f32, max: f32) -> u8 {
+    if !value.is_finite() {
+        return if value == f32::INFINITY { 255 } else { 0 };
+    }
+    let span = max - min;
+    let t = if span.abs() <= f32::EPSILON {
+        if value >= max {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        (value - min) / span
+    };
+    (t.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Decode an IEEE 754 half-precision float
+pub(crate) fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_bytes_per_pixel() {
+        assert_eq!(bytes_per_pixel(TextureFormat::Bgra8Unorm), Some(4));
+        assert_eq!(bytes_per_pixel(TextureFormat::Rgba16Float), Some(8));
+        assert_eq!(bytes_per_pixel(TextureFormat::R32Float), Some(4));
+        assert_eq!(bytes_per_pixel(TextureFormat::Depth16Unorm), Some(2));
+        assert_eq!(
+            bytes_per_pixel(TextureFormat::Depth32FloatStencil8),
+            Some(4)
+        );
+        assert_eq!(bytes_per_pixel(TextureFormat::Rgba32Uint), None);
+        assert_eq!(bytes_per_pixel(TextureFormat::Rgb10a2Unorm), Some(4));
+        assert_eq!(bytes_per_pixel(TextureFormat::Rg16Float), Some(4));
+        assert_eq!(bytes_per_pixel(TextureFormat::Bc1RgbaUnorm), None);
+    }
+
+    #[test]
+    fn test_raw_four_byte_formats_are_passed_through() {
+        for format in [TextureFormat::Rgba8Snorm, TextureFormat::Rgba8Uint] {
+            assert!(is_raw_format(format));
+            let image =
+                convert_to_rgba8(&[1, 2, 3, 4], 1, 1, format, Normalization::default()).unwrap();
+            assert_eq!(image.get_pixel(0, 0), &Rgba([1, 2, 3, 4]));
+        }
+        assert!(!is_raw_format(TextureFormat::Rgba8Unorm));
+        assert!(!is_raw_format(TextureFormat::Depth32Float));
+    }
+
+    #[test]
+    fn test_bgra_is_swizzled() {
+        let image = convert_to_rgba8(
+            &[10, 20, 30, 40],
+            1,
+            1,
+            TextureFormat::Bgra8Unorm,
+            Normalization::default(),
+        )
+        .unwrap();
+        assert_eq!(image.get_pixel(0, 0), &Rgba([30, 20, 10, 40]));
+    }
+
+    #[test]
+    fn test_f16_decoding() {
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x4900), 10.0);
+        assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_rgba16float_range() {
+        // (2.0, 1.0, 0.0, 1.0) as half floats
+        let data = [0x00, 0x40, 0x00, 0x3c, 0x00, 0x00, 0x00, 0x3c];
+        let clamped = convert_to_rgba8(
+            &data,
+            1,
+            1,
+            TextureFormat::Rgba16Float,
+            Normalization::default(),
+        )
+        .unwrap();
+        assert_eq!(clamped.get_pixel(0, 0), &Rgba([255, 255, 0, 255]));
+
+        let hdr = convert_to_rgba8(
+            &data,
+            1,
+            1,
+            TextureFormat::Rgba16Float,
+            Normalization::Range { min: 0.0, max: 2.0 },
+        )
+        .unwrap();
+        assert_eq!(hdr.get_pixel(0, 0), &Rgba([255, 128, 0, 255]));
+    }
+
+    #[test]
+    fn test_depth_auto_normalization() {
+        let data: Vec<u8> = [0.25f32, 0.5, 0.75, 0.5]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let image = convert_to_rgba8(
+            &data,
+            2,
+            2,
+            TextureFormat::Depth32Float,
+            Normalization::Auto,
+        )
+        .unwrap();
+        assert_eq!(image.get_pixel(0, 0), &Rgba([0, 0, 0, 255]));
+        assert_eq!(image.get_pixel(0, 1), &Rgba([255, 255, 255, 255]));
+        assert_eq!(image.get_pixel(1, 0)[0], 128);
+    }
+
+    #[test]
+    fn test_short_data_is_rejected() {
+        assert!(
+            convert_to_rgba8(&[0; 3], 1, 1, TextureFormat::R32Float, Normalization::Auto).is_err()
+        );
+    }
+}
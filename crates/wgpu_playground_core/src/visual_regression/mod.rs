@@ -1,9 +1,10 @@
 //! Visual regression testing framework
 //!
 //! This module provides utilities for visual regression testing of GPU rendering:
-//! - Capturing rendered output to images
+//! - Capturing rendered output to images (8-bit, HDR and depth formats, see [`formats`])
 //! - Comparing rendered output with reference images
 //! - Generating difference images to highlight discrepancies
+//! - Writing a standalone HTML report of all comparisons (see [`report`])
 //!
 //! # Example
 //!
@@ -27,9 +28,15 @@
 //! # }
 //! ```
 
+pub mod external;
+pub mod formats;
+pub mod references;
+pub mod report;
 pub mod test_utils;
 
-use image::{ImageBuffer, Rgba, RgbaImage};
+pub use formats::{CaptureOptions, Normalization};
+
+use image::{Rgba, RgbaImage};
 use std::path::PathBuf;
 use wgpu::{Device, Queue, Texture};
 
@@ -108,6 +115,9 @@ impl std::error::Error for VisualRegressionError {}
 
 /// Captures a GPU texture to an RGBA image
 ///
+/// Uses [`CaptureOptions::default`], which maps float and depth values in
+/// `0.0..=1.0` to `0..=255`.
+///
 /// # Arguments
 ///
 /// * `device` - The GPU device
@@ -121,13 +131,33 @@ pub async fn capture_texture(
     device: &Device,
     queue: &Queue,
     texture: &Texture,
+) -> Result<RgbaImage, VisualRegressionError> {
+    capture_texture_with_options(device, queue, texture, &CaptureOptions::default()).await
+}
+
+/// Captures a GPU texture to an RGBA image with explicit conversion options
+///
+/// Supports the formats listed in [`formats::SUPPORTED_FORMATS`]. BGRA data
+/// is swizzled to RGBA; `Rgba16Float`, `R32Float` and depth formats are
+/// normalized to 8 bits according to `options.normalization`. Single-channel
+/// formats are returned as grayscale. Any other uncompressed color format
+/// with 4-byte texels is returned as its raw bytes. The texture needs
+/// `COPY_SRC` usage.
+pub async fn capture_texture_with_options(
+    device: &Device,
+    queue: &Queue,
+    texture: &Texture,
+    options: &CaptureOptions,
 ) -> Result<RgbaImage, VisualRegressionError> {
     let size = texture.size();
     let width = size.width;
     let height = size.height;
+    let format = texture.format();
 
     // Create a buffer to copy texture data to
-    let bytes_per_pixel = 4; // RGBA
+    let bytes_per_pixel = formats::bytes_per_pixel(format).ok_or_else(|| {
+        VisualRegressionError::CaptureError(format!("Unsupported capture format {:?}", format))
+    })?;
     let unpadded_bytes_per_row = width * bytes_per_pixel;
     let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
     let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
@@ -149,7 +179,7 @@ pub async fn capture_texture(
             texture,
             mip_level: 0,
             origin: wgpu::Origin3d::ZERO,
-            aspect: wgpu::TextureAspect::All,
+            aspect: formats::capture_aspect(format),
         },
         wgpu::TexelCopyBufferInfo {
             buffer: &buffer,
@@ -159,7 +189,10 @@ pub async fn capture_texture(
                 rows_per_image: Some(height),
             },
         },
-        size,
+        wgpu::Extent3d {
+            depth_or_array_layers: 1,
+            ..size
+        },
     );
 
     queue.submit(Some(encoder.finish()));
@@ -184,19 +217,18 @@ pub async fn capture_texture(
 
     let data = buffer_slice.get_mapped_range();
 
-    // Create image from buffer data (handling padding)
-    let mut image_data = Vec::with_capacity((width * height * bytes_per_pixel) as usize);
+    // Strip row padding
+    let mut texel_data = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
     for row in 0..height {
         let row_start = (row * padded_bytes_per_row) as usize;
-        let row_end = row_start + (width * bytes_per_pixel) as usize;
-        image_data.extend_from_slice(&data[row_start..row_end]);
+        let row_end = row_start + unpadded_bytes_per_row as usize;
+        texel_data.extend_from_slice(&data[row_start..row_end]);
     }
 
     drop(data);
     buffer.unmap();
 
-    ImageBuffer::from_raw(width, height, image_data)
-        .ok_or_else(|| VisualRegressionError::CaptureError("Failed to create image buffer".into()))
+    formats::convert_to_rgba8(&texel_data, width, height, format, options.normalization)
 }
 
 /// Compares a captured image with a reference image
@@ -210,10 +242,29 @@ pub async fn capture_texture(
 /// # Returns
 ///
 /// Returns a comparison result indicating if images match
+///
+/// When [`report::REPORT_DIR_ENV`] is set, the outcome is also recorded in
+/// the HTML report in that directory.
 pub fn compare_with_reference(
     captured: &RgbaImage,
     test_name: &str,
     config: ComparisonConfig,
+) -> Result<ComparisonResult, VisualRegressionError> {
+    let threshold = config.threshold;
+    let result = compare_images_with_reference(captured, test_name, config);
+    match &result {
+        Ok(comparison) => report::record(report::ReportEntry::from_comparison(
+            test_name, comparison, threshold,
+        )),
+        Err(error) => report::record(report::ReportEntry::from_error(test_name, error, threshold)),
+    }
+    result
+}
+
+fn compare_images_with_reference(
+    captured: &RgbaImage,
+    test_name: &str,
+    config: ComparisonConfig,
 ) -> Result<ComparisonResult, VisualRegressionError> {
     let reference_path = get_reference_path(test_name);
     let output_path = get_output_path(test_name);
@@ -264,7 +315,45 @@ pub fn compare_with_reference(
         });
     }
 
-    // Compare images pixel by pixel
+    let (difference, diff_image) = diff_images(captured, &reference);
+    let is_match = difference <= config.threshold;
+
+    // Save diff image if there's a mismatch
+    let diff_image_path = if !is_match && config.save_diff {
+        let diff_path = get_diff_path(test_name);
+        if let Some(parent) = diff_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        diff_image.save(&diff_path).ok();
+        Some(diff_path)
+    } else {
+        None
+    };
+
+    Ok(ComparisonResult {
+        is_match,
+        difference,
+        diff_image_path,
+    })
+}
+
+/// Computes the mean per-channel difference between two images of equal size
+///
+/// Returns a value between 0.0 (identical) and 1.0 (completely different).
+pub fn image_difference(a: &RgbaImage, b: &RgbaImage) -> Result<f32, VisualRegressionError> {
+    if a.dimensions() != b.dimensions() {
+        return Err(VisualRegressionError::DimensionMismatch {
+            expected: b.dimensions(),
+            actual: a.dimensions(),
+        });
+    }
+    Ok(diff_images(a, b).0)
+}
+
+/// Compares two images of equal size pixel by pixel
+///
+/// Returns the difference metric and a visualization with differences in red.
+fn diff_images(captured: &RgbaImage, reference: &RgbaImage) -> (f32, RgbaImage) {
     let (width, height) = captured.dimensions();
     let mut total_diff = 0.0f32;
     let mut diff_image = RgbaImage::new(width, height);
@@ -289,26 +378,7 @@ pub fn compare_with_reference(
         }
     }
 
-    let difference = total_diff / (width * height) as f32;
-    let is_match = difference <= config.threshold;
-
-    // Save diff image if there's a mismatch
-    let diff_image_path = if !is_match && config.save_diff {
-        let diff_path = get_diff_path(test_name);
-        if let Some(parent) = diff_path.parent() {
-            std::fs::create_dir_all(parent).ok();
-        }
-        diff_image.save(&diff_path).ok();
-        Some(diff_path)
-    } else {
-        None
-    };
-
-    Ok(ComparisonResult {
-        is_match,
-        difference,
-        diff_image_path,
-    })
+    (total_diff / (width * height) as f32, diff_image)
 }
 
 /// Gets the path to a reference image
@@ -0,0 +1,469 @@
+//! Reference image bookkeeping for visual regression tests
+//!
+//! Used by the `reference_manager` binary to find stale and orphaned reference
+//! images, promote test outputs to references one at a time, and check that
+//! every reference belongs to a test.
+//!
+//! Test names are found by scanning test sources for string literals, which
+//! misses references used by watch mode, the server, FFI or Python bindings
+//! and names computed at run time. Those are listed one per line in the
+//! reference manifest ([`ReferenceStore::manifest_path`]). Because the scan
+//! can't prove a reference unused, [`ReferenceStore::prune`] only deletes
+//! anything when the manifest exists and no test computes its names.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Status of a single visual regression test name
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReferenceStatus {
+    /// Reference and latest output match exactly
+    UpToDate,
+    /// Latest output differs from the reference
+    Stale { difference: f32 },
+    /// Latest output has a different size than the reference
+    SizeChanged {
+        reference: (u32, u32),
+        output: (u32, u32),
+    },
+    /// Reference exists but no output has been produced yet
+    NoOutput,
+    /// A test exists but has no reference image
+    MissingReference,
+    /// Reference exists but neither a test nor the manifest names it
+    Orphaned,
+}
+
+impl ReferenceStatus {
+    /// Short label used by the CLI
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::UpToDate => "ok",
+            Self::Stale { .. } => "stale",
+            Self::SizeChanged { .. } => "resized",
+            Self::NoOutput => "no-output",
+            Self::MissingReference => "missing",
+            Self::Orphaned => "orphaned",
+        }
+    }
+
+    /// Whether the output could be promoted to a reference
+    pub fn is_promotable(&self) -> bool {
+        matches!(
+            self,
+            Self::Stale { .. } | Self::SizeChanged { .. } | Self::MissingReference
+        )
+    }
+}
+
+/// Locations of reference images, test outputs and test sources
+#[derive(Debug, Clone)]
+pub struct ReferenceStore {
+    /// Directory with committed reference images
+    pub reference_dir: PathBuf,
+    /// Directory with outputs written by the last test run
+    pub output_dir: PathBuf,
+    /// Directories scanned for test sources
+    pub test_dirs: Vec<PathBuf>,
+    /// File listing reference names used outside the scanned tests
+    pub manifest_path: PathBuf,
+}
+
+impl Default for ReferenceStore {
+    fn default() -> Self {
+        let workspace = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let reference_dir = workspace.join("tests/visual_regression/reference");
+        Self {
+            manifest_path: reference_dir.join("manifest.txt"),
+            reference_dir,
+            output_dir: workspace.join("tests/visual_regression/output"),
+            test_dirs: vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests")],
+        }
+    }
+}
+
+impl ReferenceStore {
+    /// Names of all reference images (file stem of every `.png`)
+    pub fn reference_names(&self) -> BTreeSet<String> {
+        png_stems(&self.reference_dir)
+    }
+
+    /// Names of all output images, excluding diff images
+    pub fn output_names(&self) -> BTreeSet<String> {
+        png_stems(&self.output_dir)
+            .into_iter()
+            .filter(|name| !name.ends_with("_diff"))
+            .collect()
+    }
+
+    /// Test names found in the test sources and the manifest
+    pub fn test_names(&self) -> BTreeSet<String> {
+        let mut names = self.manifest_names();
+        for source in self.test_sources() {
+            names.extend(extract_test_names(&source));
+        }
+        names
+    }
+
+    /// Names listed in the manifest
+    ///
+    /// One name per line; blank lines and lines starting with `#` are
+    /// ignored. A missing manifest lists nothing.
+    pub fn manifest_names(&self) -> BTreeSet<String> {
+        std::fs::read_to_string(&self.manifest_path)
+            .map(|text| {
+                text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Number of visual test calls whose name isn't a string literal
+    pub fn computed_name_count(&self) -> usize {
+        self.test_sources()
+            .iter()
+            .map(|source| count_computed_names(source))
+            .sum()
+    }
+
+    fn test_sources(&self) -> Vec<String> {
+        let mut sources = Vec::new();
+        for dir in &self.test_dirs {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "rs") {
+                    if let Ok(source) = std::fs::read_to_string(&path) {
+                        sources.push(source);
+                    }
+                }
+            }
+        }
+        sources
+    }
+
+    /// Path of the reference image for a test
+    pub fn reference_path(&self, name: &str) -> PathBuf {
+        self.reference_dir.join(format!("{}.png", name))
+    }
+
+    /// Path of the latest output image for a test
+    pub fn output_path(&self, name: &str) -> PathBuf {
+        self.output_dir.join(format!("{}.png", name))
+    }
+
+    /// Status of every known test and reference, sorted by name
+    pub fn statuses(&self) -> Vec<(String, ReferenceStatus)> {
+        let references = self.reference_names();
+        let tests = self.test_names();
+        let all: BTreeSet<&String> = references.iter().chain(tests.iter()).collect();
+
+        all.into_iter()
+            .map(|name| {
+                let status = if !tests.contains(name) {
+                    ReferenceStatus::Orphaned
+                } else if !references.contains(name) {
+                    ReferenceStatus::MissingReference
+                } else {
+                    self.compare(name)
+                };
+                (name.clone(), status)
+            })
+            .collect()
+    }
+
+    /// Compare a reference with its latest output
+    pub fn compare(&self, name: &str) -> ReferenceStatus {
+        let output_path = self.output_path(name);
+        if !output_path.exists() {
+            return ReferenceStatus::NoOutput;
+        }
+        let (Ok(reference), Ok(output)) = (
+            image::open(self.reference_path(name)),
+            image::open(&output_path),
+        ) else {
+            return ReferenceStatus::NoOutput;
+        };
+        let (reference, output) = (reference.to_rgba8(), output.to_rgba8());
+
+        match super::image_difference(&output, &reference) {
+            Ok(difference) if difference == 0.0 => ReferenceStatus::UpToDate,
+            Ok(difference) => ReferenceStatus::Stale { difference },
+            Err(_) => ReferenceStatus::SizeChanged {
+                reference: reference.dimensions(),
+                output: output.dimensions(),
+            },
+        }
+    }
+
+    /// Copy the latest output of a test over its reference
+    pub fn promote(&self, name: &str) -> std::io::Result<PathBuf> {
+        let source = self.output_path(name);
+        if !source.exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No output image for '{}'", name),
+            ));
+        }
+        std::fs::create_dir_all(&self.reference_dir)?;
+        let target = self.reference_path(name);
+        std::fs::copy(&source, &target)?;
+        Ok(target)
+    }
+
+    /// Delete orphaned reference images
+    ///
+    /// Returns the paths that were (or with `dry_run`, would be) removed.
+    /// Deleting requires the manifest to exist and every test name to be a
+    /// literal; otherwise a reference used elsewhere could look orphaned, so
+    /// an error is returned and nothing is removed.
+    pub fn prune(&self, dry_run: bool) -> std::io::Result<Vec<PathBuf>> {
+        if !dry_run {
+            if !self.manifest_path.exists() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!(
+                        "No reference manifest at {}; create it (listing references used \
+                         outside the tests) before deleting anything",
+                        self.manifest_path.display()
+                    ),
+                ));
+            }
+            let computed = self.computed_name_count();
+            if computed > 0 {
+                return Err(std::io::Error::other(format!(
+                    "{} visual test call(s) compute their reference name, so orphans \
+                     can't be identified",
+                    computed
+                )));
+            }
+        }
+        let tests = self.test_names();
+        let mut removed = Vec::new();
+        for name in self.reference_names() {
+            if tests.contains(&name) {
+                continue;
+            }
+            let path = self.reference_path(&name);
+            if !dry_run {
+                std::fs::remove_file(&path)?;
+            }
+            removed.push(path);
+        }
+        Ok(removed)
+    }
+}
+
+fn png_stems(dir: &Path) -> BTreeSet<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return BTreeSet::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "png"))
+        .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect()
+}
+
+/// Calls that take a reference name, with the index of the name argument
+const NAMED_CALLS: [(&str, usize); 2] = [("run_visual_test(", 0), ("compare_with_reference(", 1)];
+
+/// Extract visual regression test names from Rust test source
+///
+/// Recognizes the first string literal passed to `run_visual_test(` and the
+/// second argument of `compare_with_reference(`.
+pub fn extract_test_names(source: &str) -> BTreeSet<String> {
+    named_calls(source).flatten().collect()
+}
+
+/// Count the calls of [`extract_test_names`] whose name isn't a literal
+pub fn count_computed_names(source: &str) -> usize {
+    named_calls(source).filter(Option::is_none).count()
+}
+
+/// Name argument of every call in `source`, `None` where it isn't a literal
+fn named_calls(source: &str) -> impl Iterator<Item = Option<String>> + '_ {
+    NAMED_CALLS.into_iter().flat_map(move |(call, arg_index)| {
+        source
+            .match_indices(call)
+            .filter(|(pos, _)| !source[..*pos].trim_end().ends_with("fn"))
+            .map(move |(pos, _)| nth_argument_literal(&source[pos + call.len()..], arg_index))
+    })
+}
+
+/// Return the `index`-th top-level argument if it is a plain string literal
+fn nth_argument_literal(args: &str, index: usize) -> Option<String> {
+    let mut depth = 0usize;
+    let mut current = 0usize;
+    let mut start = 0usize;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => {
+                return if current == index {
+                    string_literal(&args[start..i])
+                } else {
+                    None
+                };
+            }
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                if current == index {
+                    return string_literal(&args[start..i]);
+                }
+                current += 1;
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn string_literal(arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    let inner = arg.strip_prefix('"')?.strip_suffix('"')?;
+    (!inner.is_empty() && !inner.contains('"')).then(|| inner.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn temp_store(name: &str) -> ReferenceStore {
+        let root = std::env::temp_dir().join(format!(
+            "wgpu_playground_refs_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        let store = ReferenceStore {
+            reference_dir: root.join("reference"),
+            output_dir: root.join("output"),
+            test_dirs: vec![root.join("tests")],
+            manifest_path: root.join("reference").join("manifest.txt"),
+        };
+        std::fs::create_dir_all(&store.reference_dir).unwrap();
+        std::fs::create_dir_all(&store.output_dir).unwrap();
+        std::fs::create_dir_all(&store.test_dirs[0]).unwrap();
+        store
+    }
+
+    fn solid(color: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_pixel(4, 4, Rgba(color))
+    }
+
+    #[test]
+    fn test_extract_test_names() {
+        let source = r#"
+            let result = run_visual_test(
+                "triangle",
+                &device,
+                &queue,
+                |d, q| render(d, q),
+                ComparisonConfig::default(),
+            ).await;
+            match compare_with_reference(&img, "xcheck_triangle", cfg) {}
+            compare_with_reference(&img, name, cfg);
+        "#;
+        let names = extract_test_names(source);
+        assert_eq!(
+            names.into_iter().collect::<Vec<_>>(),
+            vec!["triangle".to_string(), "xcheck_triangle".to_string()]
+        );
+        assert_eq!(count_computed_names(source), 1);
+        assert_eq!(
+            count_computed_names("pub fn compare_with_reference(img: &I, name: &str)"),
+            0
+        );
+    }
+
+    fn lookup_in(statuses: &[(String, ReferenceStatus)], name: &str) -> ReferenceStatus {
+        statuses
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, s)| s.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_computed_names_block_deletion() {
+        let store = temp_store("computed");
+        std::fs::write(
+            store.test_dirs[0].join("b_test.rs"),
+            r#"for name in NAMES { compare_with_reference(&img, name, cfg); }"#,
+        )
+        .unwrap();
+        std::fs::write(&store.manifest_path, "").unwrap();
+        solid([0, 0, 0, 255])
+            .save(store.reference_path("generated_0"))
+            .unwrap();
+
+        assert_eq!(store.prune(true).unwrap().len(), 1);
+        assert!(store.prune(false).is_err());
+        assert!(store.reference_path("generated_0").exists());
+    }
+
+    #[test]
+    fn test_statuses() {
+        let store = temp_store("statuses");
+        std::fs::write(
+            store.test_dirs[0].join("a_test.rs"),
+            r#"run_visual_test("same", d, q, f, c); run_visual_test("changed", d, q, f, c); run_visual_test("new", d, q, f, c);"#,
+        )
+        .unwrap();
+
+        solid([255, 0, 0, 255])
+            .save(store.reference_path("same"))
+            .unwrap();
+        solid([255, 0, 0, 255])
+            .save(store.output_path("same"))
+            .unwrap();
+        solid([0, 0, 0, 255])
+            .save(store.reference_path("changed"))
+            .unwrap();
+        solid([255, 255, 255, 255])
+            .save(store.output_path("changed"))
+            .unwrap();
+        solid([0, 0, 0, 255])
+            .save(store.reference_path("old"))
+            .unwrap();
+
+        let statuses = store.statuses();
+        let lookup = |name: &str| lookup_in(&statuses, name);
+        assert_eq!(lookup("same"), ReferenceStatus::UpToDate);
+        assert!(matches!(lookup("changed"), ReferenceStatus::Stale { .. }));
+        assert_eq!(lookup("new"), ReferenceStatus::MissingReference);
+        assert_eq!(lookup("old"), ReferenceStatus::Orphaned);
+
+        let pruned = store.prune(true).unwrap();
+        assert_eq!(pruned, vec![store.reference_path("old")]);
+        assert!(store.reference_path("old").exists());
+
+        // Nothing is deleted without a manifest
+        assert!(store.prune(false).is_err());
+        assert!(store.reference_path("old").exists());
+
+        std::fs::write(&store.manifest_path, "# used by watch mode\nold\n").unwrap();
+        assert_eq!(
+            lookup_in(&store.statuses(), "old"),
+            ReferenceStatus::NoOutput
+        );
+        assert!(store.prune(false).unwrap().is_empty());
+
+        std::fs::write(&store.manifest_path, "").unwrap();
+        store.prune(false).unwrap();
+        assert!(!store.reference_path("old").exists());
+
+        store.promote("changed").unwrap();
+        assert_eq!(store.compare("changed"), ReferenceStatus::UpToDate);
+        assert!(store.promote("new").is_err());
+    }
+}
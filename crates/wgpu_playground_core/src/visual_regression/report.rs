@@ -0,0 +1,447 @@
+//! Standalone HTML reports for visual regression runs
+//!
+//! Reporting is opt-in: when [`REPORT_DIR_ENV`] names a directory, every call
+//! to [`compare_with_reference`](super::compare_with_reference) writes its
+//! outcome there as a JSON sidecar, `entries/<test>.json`, and [`flush`]
+//! renders all sidecars in the directory into `report.html`. Each test binary
+//! runs in its own process, so merging through files is what lets one report
+//! cover the whole run. Reference, output and diff images are embedded as
+//! base64 data URIs so the report can be downloaded as a single CI artifact
+//! and opened offline.
+
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the report directory
+pub const REPORT_DIR_ENV: &str = "VISUAL_REGRESSION_REPORT_DIR";
+
+/// Outcome of a single visual regression comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportStatus {
+    /// Difference within threshold
+    Pass,
+    /// Difference above threshold
+    Fail,
+    /// Comparison could not be performed (missing reference, size mismatch, ...)
+    Error,
+}
+
+impl ReportStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Fail => "FAIL",
+            Self::Error => "ERROR",
+        }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A single row of the report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportEntry {
+    /// Name of the test
+    pub test_name: String,
+    /// Comparison outcome
+    pub status: ReportStatus,
+    /// Difference metric (0.0 - 1.0)
+    pub difference: f32,
+    /// Threshold that was applied
+    pub threshold: f32,
+    /// Reference image path, if it exists
+    pub reference_path: Option<PathBuf>,
+    /// Captured output image path, if it exists
+    pub output_path: Option<PathBuf>,
+    /// Diff image path, if one was written
+    pub diff_path: Option<PathBuf>,
+    /// Error message for [`ReportStatus::Error`] entries
+    pub message: Option<String>,
+}
+
+impl ReportEntry {
+    /// Create an entry for a successful comparison
+    pub fn from_comparison(
+        test_name: impl Into<String>,
+        result: &super::ComparisonResult,
+        threshold: f32,
+    ) -> Self {
+        let test_name = test_name.into();
+        Self {
+            status: if result.is_match {
+                ReportStatus::Pass
+            } else {
+                ReportStatus::Fail
+            },
+            difference: result.difference,
+            threshold,
+            reference_path: existing(super::get_reference_path(&test_name)),
+            output_path: existing(super::get_output_path(&test_name)),
+            diff_path: result.diff_image_path.clone(),
+            message: None,
+            test_name,
+        }
+    }
+
+    /// Create an entry for a comparison that failed with an error
+    pub fn from_error(
+        test_name: impl Into<String>,
+        error: &super::VisualRegressionError,
+        threshold: f32,
+    ) -> Self {
+        let test_name = test_name.into();
+        Self {
+            status: ReportStatus::Error,
+            difference: 1.0,
+            threshold,
+            reference_path: existing(super::get_reference_path(&test_name)),
+            output_path: existing(super::get_output_path(&test_name)),
+            diff_path: None,
+            message: Some(error.to_string()),
+            test_name,
+        }
+    }
+}
+
+fn existing(path: PathBuf) -> Option<PathBuf> {
+    path.exists().then_some(path)
+}
+
+/// Collection of comparison results rendered as a single HTML page
+#[derive(Debug, Clone, Default)]
+pub struct RegressionReport {
+    title: String,
+    entries: Vec<ReportEntry>,
+}
+
+impl RegressionReport {
+    /// Create an empty report
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add or replace the entry for a test
+    pub fn add_entry(&mut self, entry: ReportEntry) {
+        self.entries.retain(|e| e.test_name != entry.test_name);
+        self.entries.push(entry);
+    }
+
+    /// All entries in insertion order
+    pub fn entries(&self) -> &[ReportEntry] {
+        &self.entries
+    }
+
+    /// Number of entries with the given status
+    pub fn count(&self, status: ReportStatus) -> usize {
+        self.entries.iter().filter(|e| e.status == status).count()
+    }
+
+    /// Render the report as a standalone HTML document
+    ///
+    /// Failing and erroring tests are listed first.
+    pub fn to_html(&self) -> String {
+        let mut entries: Vec<&ReportEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|e| match e.status {
+            ReportStatus::Error => 0,
+            ReportStatus::Fail => 1,
+            ReportStatus::Pass => 2,
+        });
+
+        let mut body = String::new();
+        for entry in entries {
+            body.push_str(&render_entry(entry));
+        }
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>{title}</title>
+    <style>
+        body {{ font-family: sans-serif; margin: 40px; background: #f5f5f5; }}
+        h1 {{ color: #333; border-bottom: 2px solid #4CAF50; padding-bottom: 10px; }}
+        .summary span {{ margin-right: 20px; font-weight: bold; }}
+        .entry {{ background: white; margin: 20px 0; padding: 20px; border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1); border-left: 6px solid #ccc; }}
+        .entry.pass {{ border-left-color: #4CAF50; }}
+        .entry.fail {{ border-left-color: #f44336; }}
+        .entry.error {{ border-left-color: #ff9800; }}
+        .status {{ font-weight: bold; padding: 2px 8px; border-radius: 4px; color: white; }}
+        .status.pass {{ background: #4CAF50; }}
+        .status.fail {{ background: #f44336; }}
+        .status.error {{ background: #ff9800; }}
+        .images {{ display: flex; gap: 20px; margin-top: 15px; }}
+        .img-box {{ flex: 1; text-align: center; }}
+        .img-box img {{ max-width: 100%; border: 1px solid #ddd; border-radius: 4px; image-rendering: pixelated; }}
+        .img-box p {{ margin-top: 10px; font-weight: bold; color: #666; }}
+        .missing {{ color: #999; font-style: italic; padding: 40px 0; border: 1px dashed #ddd; }}
+        .message {{ color: #b71c1c; font-family: monospace; }}
+    </style>
+</head>
+<body>
+    <h1>{title}</h1>
+    <div class="summary">
+        <span>Total: {total}</span>
+        <span style="color:#4CAF50">Passed: {passed}</span>
+        <span style="color:#f44336">Failed: {failed}</span>
+        <span style="color:#ff9800">Errors: {errors}</span>
+    </div>
+    {body}
+</body>
+</html>"#,
+            title = escape_html(&self.title),
+            total = self.entries.len(),
+            passed = self.count(ReportStatus::Pass),
+            failed = self.count(ReportStatus::Fail),
+            errors = self.count(ReportStatus::Error),
+            body = body,
+        )
+    }
+
+    /// Write the report to a file, creating parent directories as needed
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_atomically(path, self.to_html().as_bytes())
+    }
+
+    /// Build a report from the sidecars in a report directory
+    ///
+    /// Entries are sorted by test name; unreadable sidecars are skipped.
+    pub fn load_dir(title: impl Into<String>, dir: &Path) -> Self {
+        let mut report = Self::new(title);
+        let Ok(files) = std::fs::read_dir(dir.join(ENTRIES_DIR)) else {
+            return report;
+        };
+        let mut entries: Vec<ReportEntry> = files
+            .flatten()
+            .map(|file| file.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| std::fs::read(path).ok())
+            .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+            .collect();
+        entries.sort_by(|a: &ReportEntry, b| a.test_name.cmp(&b.test_name));
+        for entry in entries {
+            report.add_entry(entry);
+        }
+        report
+    }
+}
+
+fn render_entry(entry: &ReportEntry) -> String {
+    let message = entry
+        .message
+        .as_ref()
+        .map(|m| format!(r#"<p class="message">{}</p>"#, escape_html(m)))
+        .unwrap_or_default();
+
+    format!(
+        r#"
+    <div class="entry {class}">
+        <h2>{name} <span class="status {class}">{status}</span></h2>
+        <p>Difference: {difference:.4}% (threshold {threshold:.4}%)</p>
+        {message}
+        <div class="images">
+            {reference}
+            {output}
+            {diff}
+        </div>
+    </div>
+"#,
+        class = entry.status.css_class(),
+        name = escape_html(&entry.test_name),
+        status = entry.status.label(),
+        difference = entry.difference * 100.0,
+        threshold = entry.threshold * 100.0,
+        message = message,
+        reference = image_box("Reference", entry.reference_path.as_deref()),
+        output = image_box("Output", entry.output_path.as_deref()),
+        diff = image_box("Diff", entry.diff_path.as_deref()),
+    )
+}
+
+fn image_box(caption: &str, path: Option<&Path>) -> String {
+    match path.and_then(png_data_uri) {
+        Some(uri) => format!(
+            r#"<div class="img-box"><img src="{}" alt="{}"/><p>{}</p></div>"#,
+            uri, caption, caption
+        ),
+        None => format!(
+            r#"<div class="img-box"><div class="missing">No image</div><p>{}</p></div>"#,
+            caption
+        ),
+    }
+}
+
+/// Read an image file and encode it as a `data:image/png;base64,...` URI
+fn png_data_uri(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(format!(
+        "data:image/png;base64,{}",
+        BASE64_STANDARD.encode(bytes)
+    ))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Subdirectory of the report directory holding one sidecar per test
+const ENTRIES_DIR: &str = "entries";
+
+/// Report directory configured through [`REPORT_DIR_ENV`], if any
+pub fn report_dir() -> Option<PathBuf> {
+    std::env::var_os(REPORT_DIR_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Write the sidecar for an entry into a report directory
+///
+/// A later entry for the same test replaces the earlier one.
+pub fn record_to(dir: &Path, entry: &ReportEntry) -> std::io::Result<PathBuf> {
+    let entries = dir.join(ENTRIES_DIR);
+    std::fs::create_dir_all(&entries)?;
+    let path = entries.join(format!("{}.json", sidecar_stem(&entry.test_name)));
+    let json = serde_json::to_vec_pretty(entry).map_err(std::io::Error::other)?;
+    write_atomically(&path, &json)?;
+    Ok(path)
+}
+
+/// Render every sidecar in a report directory into `report.html`
+///
+/// Returns the path of the written report.
+pub fn flush(dir: &Path) -> std::io::Result<PathBuf> {
+    let path = dir.join("report.html");
+    RegressionReport::load_dir("Visual Regression Report", dir).save_to(&path)?;
+    Ok(path)
+}
+
+/// Record an entry if reporting is enabled through [`REPORT_DIR_ENV`]
+///
+/// Writes the entry's sidecar and re-renders the report so that it always
+/// includes every process that has reported so far. Failures are logged and
+/// otherwise ignored so that a reporting problem never masks the actual test
+/// result.
+pub fn record(entry: ReportEntry) {
+    let Some(dir) = report_dir() else {
+        return;
+    };
+    if let Err(e) = record_to(&dir, &entry).and_then(|_| flush(&dir)) {
+        log::warn!("Failed to write visual regression report: {}", e);
+    }
+}
+
+/// File name for a test's sidecar, without characters that paths reject
+fn sidecar_stem(test_name: &str) -> String {
+    test_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Write through a temporary file so concurrent readers never see a partial file
+fn write_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let temp = path.with_extension(format!("tmp{}", std::process::id()));
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, status: ReportStatus) -> ReportEntry {
+        ReportEntry {
+            test_name: name.to_string(),
+            status,
+            difference: 0.02,
+            threshold: 0.01,
+            reference_path: None,
+            output_path: None,
+            diff_path: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_and_replacement() {
+        let mut report = RegressionReport::new("Test");
+        report.add_entry(entry("a", ReportStatus::Pass));
+        report.add_entry(entry("b", ReportStatus::Fail));
+        report.add_entry(entry("b", ReportStatus::Pass));
+        assert_eq!(report.entries().len(), 2);
+        assert_eq!(report.count(ReportStatus::Pass), 2);
+        assert_eq!(report.count(ReportStatus::Fail), 0);
+    }
+
+    #[test]
+    fn test_html_contains_status_and_metrics() {
+        let mut report = RegressionReport::new("Run <1>");
+        report.add_entry(entry("triangle", ReportStatus::Fail));
+        let mut errored = entry("quad", ReportStatus::Error);
+        errored.message = Some("Reference image not found".to_string());
+        report.add_entry(errored);
+
+        let html = report.to_html();
+        assert!(html.contains("<!DOCTYPE html>"));
+        assert!(html.contains("Run &lt;1&gt;"));
+        assert!(html.contains("triangle"));
+        assert!(html.contains("FAIL"));
+        assert!(html.contains("2.0000%"));
+        assert!(html.contains("Reference image not found"));
+        assert!(html.contains("No image"));
+        // Errors are listed before failures
+        assert!(html.find("quad").unwrap() < html.find("triangle").unwrap());
+    }
+
+    #[test]
+    fn test_sidecars_merge_into_one_report() {
+        let dir =
+            std::env::temp_dir().join(format!("wgpu_playground_report_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // As if written by two test processes
+        record_to(&dir, &entry("b/quad", ReportStatus::Fail)).unwrap();
+        record_to(&dir, &entry("a", ReportStatus::Fail)).unwrap();
+        record_to(&dir, &entry("a", ReportStatus::Pass)).unwrap();
+
+        let report = RegressionReport::load_dir("Merged", &dir);
+        let names: Vec<&str> = report
+            .entries()
+            .iter()
+            .map(|e| e.test_name.as_str())
+            .collect();
+        assert_eq!(names, ["a", "b/quad"]);
+        assert_eq!(report.count(ReportStatus::Pass), 1);
+
+        let html = std::fs::read_to_string(flush(&dir).unwrap()).unwrap();
+        assert!(html.contains("b/quad"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_missing_image_has_no_data_uri() {
+        assert!(png_data_uri(Path::new("/nonexistent/image.png")).is_none());
+    }
+}
@@ -0,0 +1,1252 @@
+//! Volumetric fog raymarched through a 3D noise texture
+//!
+//! The scene is drawn into offscreen color and depth textures first. A
+//! fullscreen pass then marches a ray through a layer of fog for every
+//! pixel, reconstructing the point the ray hits from the scene depth so the
+//! march stops at the geometry: near objects get little fog, the horizon a
+//! lot. The fog density comes from a tiling 3D noise texture scrolling with
+//! the wind. Marching with few steps bands the fog into slices, so each
+//! ray starts at an offset taken from a blue-noise texture and shifted
+//! every frame; the result is blended with the previous frame's fog,
+//! reprojected with the previous camera, which averages the jitter away.
+//! A last pass composites the fog over the scene.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{mat4_inverse, Mat4, MAT4_IDENTITY};
+use crate::upload_belt;
+use crate::water::{add_box, SceneVertex};
+use bytemuck::Zeroable;
+
+/// Side of the cubic noise texture, in texels
+pub const NOISE_SIZE: u32 = 64;
+
+/// Side of the blue-noise texture, in texels
+pub const BLUE_NOISE_SIZE: u32 = 64;
+
+/// File of the blue-noise texture in the textures assets directory
+pub const BLUE_NOISE_FILE: &str = "blue_noise_64.png";
+
+/// Height of the top of the fog layer above the ground
+pub const FOG_HEIGHT: f32 = 3.0;
+
+/// Share of the previous frame kept by the temporal blend
+pub const HISTORY_WEIGHT: f32 = 0.9;
+
+/// Side of the square ground
+const GROUND_SIZE: f32 = 40.0;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// In-scattered light and transmittance of the fog
+const FOG_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Frame offset of the jitter, the fractional part of the golden ratio:
+/// successive offsets fill the unit interval evenly
+const GOLDEN_RATIO_FRACTION: f32 = 0.618_034;
+
+pub const FOG_SHADER: &str = r#"// Volumetric Fog Example
+//
+// The scene is drawn into offscreen color and depth textures. The fog pass
+// marches a ray for every pixel from the camera to the scene depth through
+// a layer of fog whose density comes from a 3D noise texture, starting at
+// a jittered offset, and blends the result with the reprojected previous
+// frame. The composite pass attenuates the scene by the fog's
+// transmittance and adds the light the fog scatters towards the camera.
+
+struct FogUniforms {
+    view_proj: mat4x4<f32>,
+    inv_view_proj: mat4x4<f32>,
+    prev_view_proj: mat4x4<f32>,
+    eye: vec3<f32>,
+    time: f32,
+    density: f32,
+    steps: u32,
+    frame: u32,
+    // 0 none, 1 white noise, 2 blue noise
+    jitter_mode: u32,
+    history_weight: f32,
+    // 0 composite, 1 scene, 2 fog, 3 transmittance, 4 depth
+    view_mode: u32,
+    fog_height: f32,
+    anisotropy: f32,
+};
+
+@group(0) @binding(0) var<uniform> fog: FogUniforms;
+
+// Direction towards the sun
+const SUN: vec3<f32> = vec3<f32>(0.577, 0.577, -0.577);
+const SUN_COLOR: vec3<f32> = vec3<f32>(1.0, 0.92, 0.8);
+const AMBIENT: vec3<f32> = vec3<f32>(0.35, 0.42, 0.55);
+const WIND: vec3<f32> = vec3<f32>(0.6, 0.0, 0.25);
+const NOISE_SCALE: f32 = 0.08;
+const MAX_DISTANCE: f32 = 60.0;
+const GOLDEN_RATIO_FRACTION: f32 = 0.618034;
+const PI: f32 = 3.14159265;
+
+struct SceneInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+struct SceneOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) color: vec3<f32>,
+};
+
+@vertex
+fn vs_scene(in: SceneInput) -> SceneOutput {
+    var out: SceneOutput;
+    out.clip_position = fog.view_proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_scene(in: SceneOutput) -> @location(0) vec4<f32> {
+    let diffuse = max(dot(normalize(in.normal), SUN), 0.0);
+    return vec4<f32>(in.color * (0.3 + 0.7 * diffuse), 1.0);
+}
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// Depth bound as unfilterable floats, which every backend can load
+@group(1) @binding(0) var scene_depth: texture_2d<f32>;
+@group(1) @binding(1) var noise_texture: texture_3d<f32>;
+@group(1) @binding(2) var blue_noise: texture_2d<f32>;
+@group(1) @binding(3) var history: texture_2d<f32>;
+@group(1) @binding(4) var noise_sampler: sampler;
+@group(1) @binding(5) var history_sampler: sampler;
+
+// World position of a pixel at a depth buffer value
+fn world_position(pixel: vec2<f32>, depth: f32) -> vec3<f32> {
+    let uv = pixel / vec2<f32>(textureDimensions(scene_depth));
+    let ndc = vec2<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0);
+    let position = fog.inv_view_proj * vec4<f32>(ndc, depth, 1.0);
+    return position.xyz / position.w;
+}
+
+fn density_at(position: vec3<f32>) -> f32 {
+    if (position.y < 0.0 || position.y > fog.fog_height) {
+        return 0.0;
+    }
+    let uvw = position * NOISE_SCALE + WIND * NOISE_SCALE * fog.time;
+    let noise = textureSampleLevel(noise_texture, noise_sampler, uvw, 0.0).r;
+    // Thin out towards the top of the layer
+    let falloff = 1.0 - position.y / fog.fog_height;
+    return fog.density * saturate(noise * 1.6 - 0.35) * falloff;
+}
+
+// Share of the light scattered from the sun direction into the view ray
+fn henyey_greenstein(cos_theta: f32, g: f32) -> f32 {
+    let denominator = 1.0 + g * g - 2.0 * g * cos_theta;
+    return (1.0 - g * g) / (4.0 * PI * denominator * sqrt(denominator));
+}
+
+fn hash(pixel: vec2<u32>, frame: u32) -> f32 {
+    var state = pixel.x * 1973u + pixel.y * 9277u + frame * 26699u;
+    state = state ^ (state >> 16u);
+    state = state * 0x7feb352du;
+    state = state ^ (state >> 15u);
+    state = state * 0x846ca68bu;
+    state = state ^ (state >> 16u);
+    return f32(state) / 4294967295.0;
+}
+
+// Offset of the first sample along the ray, in steps
+fn jitter(pixel: vec2<u32>) -> f32 {
+    switch fog.jitter_mode {
+        case 1u: {
+            return hash(pixel, fog.frame);
+        }
+        case 2u: {
+            let size = textureDimensions(blue_noise);
+            let noise = textureLoad(blue_noise, pixel % size, 0).r;
+            return fract(noise + f32(fog.frame) * GOLDEN_RATIO_FRACTION);
+        }
+        default: {
+            return 0.5;
+        }
+    }
+}
+
+@fragment
+fn fs_fog(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let pixel = vec2<u32>(frag_coord.xy);
+    let depth = textureLoad(scene_depth, pixel, 0).r;
+    let far_point = world_position(frag_coord.xy, 1.0);
+    let direction = normalize(far_point - fog.eye);
+    var end = MAX_DISTANCE;
+    if (depth < 1.0) {
+        end = min(distance(world_position(frag_coord.xy, depth), fog.eye), end);
+    }
+
+    // Clip the ray to the fog layer
+    var start = 0.0;
+    if (abs(direction.y) > 1e-5) {
+        let t0 = (0.0 - fog.eye.y) / direction.y;
+        let t1 = (fog.fog_height - fog.eye.y) / direction.y;
+        start = max(start, min(t0, t1));
+        end = min(end, max(t0, t1));
+    } else if (fog.eye.y < 0.0 || fog.eye.y > fog.fog_height) {
+        end = start;
+    }
+
+    var light = vec3<f32>(0.0);
+    var transmittance = 1.0;
+    if (end > start) {
+        let step_length = (end - start) / f32(fog.steps);
+        let phase = henyey_greenstein(dot(direction, SUN), fog.anisotropy);
+        let sun = SUN_COLOR * phase * 4.0 * PI;
+        var t = start + step_length * jitter(pixel);
+        for (var i = 0u; i < fog.steps; i++) {
+            let position = fog.eye + direction * t;
+            let density = density_at(position);
+            if (density > 0.0) {
+                // One sample towards the sun for self-shadowing
+                let shadow = exp(-density_at(position + SUN * 0.5) * 0.5);
+                let absorbed = 1.0 - exp(-density * step_length);
+                light += transmittance * absorbed * (sun * shadow + AMBIENT);
+                transmittance *= 1.0 - absorbed;
+            }
+            t += step_length;
+        }
+    }
+    let current = vec4<f32>(light, transmittance);
+
+    if (fog.history_weight <= 0.0) {
+        return current;
+    }
+    // Where the surface behind this pixel was in the previous frame
+    let anchor = fog.eye + direction * end;
+    let previous = fog.prev_view_proj * vec4<f32>(anchor, 1.0);
+    let previous_uv = vec2<f32>(0.5, -0.5) * previous.xy / previous.w + 0.5;
+    if (previous.w <= 0.0 || any(previous_uv < vec2<f32>(0.0)) || any(previous_uv > vec2<f32>(1.0))) {
+        return current;
+    }
+    let past = textureSampleLevel(history, history_sampler, previous_uv, 0.0);
+    return mix(current, past, fog.history_weight);
+}
+
+// The composite pass shares the depth binding and reads two more
+@group(1) @binding(6) var scene_color: texture_2d<f32>;
+@group(1) @binding(7) var fog_texture: texture_2d<f32>;
+
+@fragment
+fn fs_composite(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let pixel = vec2<u32>(frag_coord.xy);
+    let scene = textureLoad(scene_color, pixel, 0).rgb;
+    let fog_sample = textureLoad(fog_texture, pixel, 0);
+    switch fog.view_mode {
+        case 1u: {
+            return vec4<f32>(scene, 1.0);
+        }
+        case 2u: {
+            return vec4<f32>(fog_sample.rgb, 1.0);
+        }
+        case 3u: {
+            return vec4<f32>(vec3<f32>(fog_sample.a), 1.0);
+        }
+        case 4u: {
+            // White at the far end of the march
+            let depth = textureLoad(scene_depth, pixel, 0).r;
+            let distance = distance(world_position(frag_coord.xy, depth), fog.eye);
+            return vec4<f32>(vec3<f32>(saturate(distance / MAX_DISTANCE)), 1.0);
+        }
+        default: {
+            return vec4<f32>(scene * fog_sample.a + fog_sample.rgb, 1.0);
+        }
+    }
+}
+"#;
+
+/// What the composite pass shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FogView {
+    /// The scene seen through the fog
+    Composite,
+    /// The scene without fog
+    Scene,
+    /// The light the fog scatters towards the camera
+    Fog,
+    /// How much of the scene shows through the fog
+    Transmittance,
+    /// Distance to the scene depth the rays stop at
+    Depth,
+}
+
+impl FogView {
+    pub const ALL: [FogView; 5] = [
+        FogView::Composite,
+        FogView::Scene,
+        FogView::Fog,
+        FogView::Transmittance,
+        FogView::Depth,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FogView::Composite => "Composite",
+            FogView::Scene => "Scene",
+            FogView::Fog => "Fog",
+            FogView::Transmittance => "Transmittance",
+            FogView::Depth => "Depth",
+        }
+    }
+
+    fn index(&self) -> u32 {
+        match self {
+            FogView::Composite => 0,
+            FogView::Scene => 1,
+            FogView::Fog => 2,
+            FogView::Transmittance => 3,
+            FogView::Depth => 4,
+        }
+    }
+}
+
+/// Where along the first step each ray starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FogJitter {
+    /// Every ray starts half a step in, which bands the fog into slices
+    None,
+    /// A hash of the pixel and frame, which trades the bands for grain
+    WhiteNoise,
+    /// The blue-noise texture shifted every frame, whose grain is finer and
+    /// averages out faster
+    BlueNoise,
+}
+
+impl FogJitter {
+    pub const ALL: [FogJitter; 3] = [FogJitter::None, FogJitter::WhiteNoise, FogJitter::BlueNoise];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FogJitter::None => "None",
+            FogJitter::WhiteNoise => "White noise",
+            FogJitter::BlueNoise => "Blue noise",
+        }
+    }
+
+    fn index(&self) -> u32 {
+        match self {
+            FogJitter::None => 0,
+            FogJitter::WhiteNoise => 1,
+            FogJitter::BlueNoise => 2,
+        }
+    }
+}
+
+/// Offset added to the blue noise in frame `frame`, in `[0, 1)`
+pub fn jitter_offset(frame: u32) -> f32 {
+    (frame as f32 * GOLDEN_RATIO_FRACTION).fract()
+}
+
+/// Henyey-Greenstein phase function: the share of light scattered by an
+/// angle with cosine `cos_theta`, for anisotropy `g` in `(-1, 1)`
+///
+/// Positive `g` scatters forwards, so fog glows when looking towards the
+/// sun.
+pub fn henyey_greenstein(cos_theta: f32, g: f32) -> f32 {
+    let denominator = 1.0 + g * g - 2.0 * g * cos_theta;
+    (1.0 - g * g) / (4.0 * std::f32::consts::PI * denominator * denominator.sqrt())
+}
+
+/// Value noise on a lattice repeating every `period` cells, at a point in
+/// cell units
+fn value_noise(p: [f32; 3], period: u32) -> f32 {
+    let lattice = |x: i64, y: i64, z: i64| {
+        let wrap = |v: i64| v.rem_euclid(i64::from(period)) as u32;
+        let mut h = wrap(x)
+            .wrapping_mul(0x8da6_b343)
+            .wrapping_add(wrap(y).wrapping_mul(0xd816_3841))
+            .wrapping_add(wrap(z).wrapping_mul(0xcb1a_b31f));
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2c1b_3c6d);
+        h ^= h >> 12;
+        (h & 0xffff) as f32 / 65535.0
+    };
+    let cell = p.map(|v| v.floor());
+    let [fx, fy, fz] = std::array::from_fn(|i| {
+        let f = p[i] - cell[i];
+        f * f * (3.0 - 2.0 * f)
+    });
+    let [x, y, z] = cell.map(|v| v as i64);
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    let plane = |z| {
+        lerp(
+            lerp(lattice(x, y, z), lattice(x + 1, y, z), fx),
+            lerp(lattice(x, y + 1, z), lattice(x + 1, y + 1, z), fx),
+            fy,
+        )
+    };
+    lerp(plane(z), plane(z + 1), fz)
+}
+
+/// R8 texels of a `size`³ volume of fractal value noise, x fastest
+///
+/// Four octaves whose lattices divide `size`, so the volume tiles in
+/// every direction.
+pub fn noise_volume(size: u32) -> Vec<u8> {
+    let mut texels = Vec::with_capacity((size * size * size) as usize);
+    for z in 0..size {
+        for y in 0..size {
+            for x in 0..size {
+                let mut value = 0.0;
+                let mut amplitude = 0.5;
+                let mut period = 4;
+                for _ in 0..4 {
+                    let scale = period as f32 / size as f32;
+                    let p = [x as f32 * scale, y as f32 * scale, z as f32 * scale];
+                    value += value_noise(p, period) * amplitude;
+                    amplitude *= 0.5;
+                    period *= 2;
+                }
+                // The octaves' amplitudes sum to 15/16
+                texels.push((value / 0.9375 * 255.0).round() as u8);
+            }
+        }
+    }
+    texels
+}
+
+/// Decode the blue-noise texture into R8 texels
+///
+/// A file of the same name in the assets directory replaces the built-in
+/// texture.
+pub fn blue_noise() -> Result<Vec<u8>, String> {
+    let bytes = crate::assets::load_texture(BLUE_NOISE_FILE)
+        .unwrap_or_else(|_| include_bytes!("../../../assets/textures/blue_noise_64.png").to_vec());
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| format!("Failed to decode {}: {}", BLUE_NOISE_FILE, e))?
+        .to_luma8();
+    if image.dimensions() != (BLUE_NOISE_SIZE, BLUE_NOISE_SIZE) {
+        return Err(format!(
+            "{} must be {}x{}, not {}x{}",
+            BLUE_NOISE_FILE,
+            BLUE_NOISE_SIZE,
+            BLUE_NOISE_SIZE,
+            image.width(),
+            image.height()
+        ));
+    }
+    Ok(image.into_raw())
+}
+
+/// Ground with a few pillars and walls for the fog to flow around
+pub fn scene_mesh() -> (Vec<SceneVertex>, Vec<u16>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let half = GROUND_SIZE / 2.0;
+
+    const TILES: u32 = 10;
+    let tile = GROUND_SIZE / TILES as f32;
+    for z in 0..TILES {
+        for x in 0..TILES {
+            let color = if (x + z) % 2 == 0 {
+                [0.45, 0.55, 0.35]
+            } else {
+                [0.38, 0.47, 0.3]
+            };
+            let (x0, z0) = (-half + x as f32 * tile, -half + z as f32 * tile);
+            add_box(
+                &mut vertices,
+                &mut indices,
+                [x0, -0.1, z0],
+                [x0 + tile, 0.0, z0 + tile],
+                color,
+            );
+        }
+    }
+
+    for ([x, z], height, color) in [
+        ([-4.0, -3.0], 4.0, [0.75, 0.3, 0.25]),
+        ([3.0, -8.0], 6.0, [0.85, 0.7, 0.3]),
+        ([6.5, 2.0], 2.0, [0.3, 0.55, 0.75]),
+        ([-9.0, -12.0], 5.0, [0.6, 0.6, 0.65]),
+        ([-1.0, 4.0], 1.2, [0.65, 0.4, 0.7]),
+    ] {
+        add_box(
+            &mut vertices,
+            &mut indices,
+            [x - 0.6, 0.0, z - 0.6],
+            [x + 0.6, height, z + 0.6],
+            color,
+        );
+    }
+    let wall = [0.55, 0.52, 0.5];
+    for (min, max) in [
+        ([-14.0, 0.0, -16.0], [14.0, 2.5, -15.0]),
+        ([10.0, 0.0, -10.0], [11.0, 1.5, 6.0]),
+    ] {
+        add_box(&mut vertices, &mut indices, min, max, wall);
+    }
+    (vertices, indices)
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct FogUniforms {
+    view_proj: Mat4,
+    inv_view_proj: Mat4,
+    prev_view_proj: Mat4,
+    eye: [f32; 3],
+    time: f32,
+    density: f32,
+    steps: u32,
+    frame: u32,
+    jitter_mode: u32,
+    history_weight: f32,
+    view_mode: u32,
+    fog_height: f32,
+    anisotropy: f32,
+}
+
+/// Textures that follow the frame size, and the bind groups reading them
+struct FrameTargets {
+    scene_color: wgpu::TextureView,
+    scene_depth: wgpu::TextureView,
+    /// Fog of this frame and the last, swapped every frame
+    fog: [wgpu::TextureView; 2],
+    /// Fog pass writing `fog[i]` and reading the other as history
+    fog_bind_groups: [wgpu::BindGroup; 2],
+    /// Composite pass reading `fog[i]`
+    composite_bind_groups: [wgpu::BindGroup; 2],
+}
+
+/// Renderer of the volumetric fog example
+pub struct FogScene {
+    scene_pipeline: wgpu::RenderPipeline,
+    fog_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    uniform_buffer: wgpu::Buffer,
+    uniform_bind_group: wgpu::BindGroup,
+    fog_layout: wgpu::BindGroupLayout,
+    composite_layout: wgpu::BindGroupLayout,
+    noise_view: wgpu::TextureView,
+    blue_noise_view: wgpu::TextureView,
+    noise_sampler: wgpu::Sampler,
+    history_sampler: wgpu::Sampler,
+    targets: FrameTargets,
+    color_format: wgpu::TextureFormat,
+    size: (u32, u32),
+    /// Frames rendered, which picks the jitter offset and the fog texture
+    frame: u32,
+    /// View-projection of the last update, reprojecting the history
+    view_proj: Mat4,
+    prev_view_proj: Mat4,
+    /// Whether the other fog texture holds a usable previous frame
+    history_valid: bool,
+    /// Extinction per unit of distance in the densest fog
+    pub density: f32,
+    /// Samples along each ray
+    pub steps: u32,
+    pub jitter: FogJitter,
+    /// Blend each frame with the reprojected previous one
+    pub temporal: bool,
+    /// Forward scattering of the sunlight, from -0.9 to 0.9
+    pub anisotropy: f32,
+    pub view: FogView,
+}
+
+impl FogScene {
+    /// Create the renderer drawing into `color_format` targets of `width`x`height`
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Volumetric Fog Shader"),
+            source: wgpu::ShaderSource::Wgsl(FOG_SHADER.into()),
+        });
+
+        let (vertices, indices) = scene_mesh();
+        let buffer = |label, contents: &[u8], usage| {
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: contents.len() as u64,
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            queue.write_buffer(&buffer, 0, contents);
+            buffer
+        };
+        let vertex_buffer = buffer(
+            "Fog Scene Vertex Buffer",
+            bytemuck::cast_slice(&vertices),
+            wgpu::BufferUsages::VERTEX,
+        );
+        let index_buffer = buffer(
+            "Fog Scene Index Buffer",
+            bytemuck::cast_slice(&indices),
+            wgpu::BufferUsages::INDEX,
+        );
+        let uniform_buffer = buffer(
+            "Fog Uniform Buffer",
+            bytemuck::bytes_of(&FogUniforms::zeroed()),
+            wgpu::BufferUsages::UNIFORM,
+        );
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let uniform_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Fog Uniform Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let texture_entry = |binding, sample_type, view_dimension| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type,
+                view_dimension,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let filterable = wgpu::TextureSampleType::Float { filterable: true };
+        let depth_entry = texture_entry(
+            0,
+            wgpu::TextureSampleType::Float { filterable: false },
+            wgpu::TextureViewDimension::D2,
+        );
+        let sampler_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        };
+        let fog_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Fog Raymarch Bind Group Layout"),
+            entries: &[
+                depth_entry,
+                texture_entry(1, filterable, wgpu::TextureViewDimension::D3),
+                texture_entry(2, filterable, wgpu::TextureViewDimension::D2),
+                texture_entry(3, filterable, wgpu::TextureViewDimension::D2),
+                sampler_entry(4),
+                sampler_entry(5),
+            ],
+        });
+        let composite_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Fog Composite Bind Group Layout"),
+            entries: &[
+                depth_entry,
+                texture_entry(6, filterable, wgpu::TextureViewDimension::D2),
+                texture_entry(7, filterable, wgpu::TextureViewDimension::D2),
+            ],
+        });
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Fog Uniform Bind Group"),
+            layout: &uniform_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let noise_view = create_noise_texture(device, queue);
+        let blue_noise_view = create_blue_noise_texture(device, queue);
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let noise_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Fog Noise Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            address_mode_w: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let history_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Fog History Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let scene_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Fog Scene Pipeline Layout"),
+                bind_group_layouts: &[Some(&uniform_layout)],
+                immediate_size: 0,
+            });
+        let fog_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Fog Raymarch Pipeline Layout"),
+            bind_group_layouts: &[Some(&uniform_layout), Some(&fog_layout)],
+            immediate_size: 0,
+        });
+        let composite_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Fog Composite Pipeline Layout"),
+                bind_group_layouts: &[Some(&uniform_layout), Some(&composite_layout)],
+                immediate_size: 0,
+            });
+
+        let vertex_attributes =
+            wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x3];
+        let scene_buffers = [wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<SceneVertex>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &vertex_attributes,
+        }];
+        let render_pipeline = |label, layout, entry_points: [&str; 2], buffers, format, depth| {
+            tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(entry_points[0]),
+                    buffers,
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_points[1]),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: depth,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+        let scene_pipeline = render_pipeline(
+            "Fog Scene Pipeline",
+            &scene_pipeline_layout,
+            ["vs_scene", "fs_scene"],
+            &scene_buffers,
+            color_format,
+            Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: Some(true),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+        );
+        let fog_pipeline = render_pipeline(
+            "Fog Raymarch Pipeline",
+            &fog_pipeline_layout,
+            ["vs_fullscreen", "fs_fog"],
+            &[],
+            FOG_FORMAT,
+            None,
+        );
+        let composite_pipeline = render_pipeline(
+            "Fog Composite Pipeline",
+            &composite_pipeline_layout,
+            ["vs_fullscreen", "fs_composite"],
+            &[],
+            color_format,
+            None,
+        );
+
+        let targets = create_frame_targets(
+            device,
+            &fog_layout,
+            &composite_layout,
+            color_format,
+            (width, height),
+            [&noise_view, &blue_noise_view],
+            [&noise_sampler, &history_sampler],
+        );
+        Self {
+            scene_pipeline,
+            fog_pipeline,
+            composite_pipeline,
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+            uniform_buffer,
+            uniform_bind_group,
+            fog_layout,
+            composite_layout,
+            noise_view,
+            blue_noise_view,
+            noise_sampler,
+            history_sampler,
+            targets,
+            color_format,
+            size: (width, height),
+            frame: 0,
+            view_proj: MAT4_IDENTITY,
+            prev_view_proj: MAT4_IDENTITY,
+            history_valid: false,
+            density: 0.35,
+            steps: 24,
+            jitter: FogJitter::BlueNoise,
+            temporal: true,
+            anisotropy: 0.5,
+            view: FogView::Composite,
+        }
+    }
+
+    /// Frames rendered so far
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Update the camera and the wind time
+    pub fn update(&mut self, queue: &wgpu::Queue, view_proj: Mat4, eye: [f32; 3], time: f32) {
+        self.prev_view_proj = self.view_proj;
+        self.view_proj = view_proj;
+        let history_weight = if self.temporal && self.history_valid {
+            HISTORY_WEIGHT
+        } else {
+            0.0
+        };
+        let uniforms = FogUniforms {
+            view_proj,
+            inv_view_proj: mat4_inverse(&view_proj).unwrap_or(MAT4_IDENTITY),
+            prev_view_proj: self.prev_view_proj,
+            eye,
+            time,
+            density: self.density,
+            steps: self.steps.max(1),
+            frame: self.frame,
+            jitter_mode: self.jitter.index(),
+            history_weight,
+            view_mode: self.view.index(),
+            fog_height: FOG_HEIGHT,
+            anisotropy: self.anisotropy.clamp(-0.9, 0.9),
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+    }
+
+    /// Render the scene, the fog and their composite into `target`, a view
+    /// of a texture of the given size
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        if self.size != (width, height) {
+            self.resize(device, width, height);
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let current = (self.frame % 2) as usize;
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Volumetric Fog Encoder"),
+        });
+        {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Fog Scene Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.targets.scene_color,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                // Kept for the fog and composite passes to sample
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.targets.scene_depth,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.scene_pipeline);
+            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            tracker.record(ApiCategory::RenderPass, "draw_indexed");
+            render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+        }
+
+        let fullscreen_passes = [
+            (
+                "Fog Raymarch Pass",
+                &self.targets.fog[current],
+                &self.fog_pipeline,
+                &self.targets.fog_bind_groups[current],
+            ),
+            (
+                "Fog Composite Pass",
+                target,
+                &self.composite_pipeline,
+                &self.targets.composite_bind_groups[current],
+            ),
+        ];
+        for (label, view, pipeline, bind_group) in fullscreen_passes {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            render_pass.set_bind_group(1, bind_group, &[]);
+            tracker.record(ApiCategory::RenderPass, "draw");
+            render_pass.draw(0..3, 0..1);
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+
+        self.frame = self.frame.wrapping_add(1);
+        self.history_valid = true;
+    }
+
+    /// Recreate the size-dependent targets; the history is lost
+    fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        self.targets = create_frame_targets(
+            device,
+            &self.fog_layout,
+            &self.composite_layout,
+            self.color_format,
+            (width, height),
+            [&self.noise_view, &self.blue_noise_view],
+            [&self.noise_sampler, &self.history_sampler],
+        );
+        self.size = (width, height);
+        self.history_valid = false;
+    }
+}
+
+fn create_texture(
+    device: &wgpu::Device,
+    label: &str,
+    format: wgpu::TextureFormat,
+    (width, height): (u32, u32),
+) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn create_frame_targets(
+    device: &wgpu::Device,
+    fog_layout: &wgpu::BindGroupLayout,
+    composite_layout: &wgpu::BindGroupLayout,
+    color_format: wgpu::TextureFormat,
+    size: (u32, u32),
+    [noise, blue_noise]: [&wgpu::TextureView; 2],
+    [noise_sampler, history_sampler]: [&wgpu::Sampler; 2],
+) -> FrameTargets {
+    let scene_color = create_texture(device, "Fog Scene Color Texture", color_format, size);
+    let scene_depth = create_texture(device, "Fog Scene Depth Texture", DEPTH_FORMAT, size);
+    let fog = [
+        create_texture(device, "Fog Texture A", FOG_FORMAT, size),
+        create_texture(device, "Fog Texture B", FOG_FORMAT, size),
+    ];
+
+    let tracker = ApiCoverageTracker::global();
+    let fog_bind_groups = [0, 1].map(|current| {
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Fog Raymarch Bind Group"),
+            layout: fog_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&scene_depth),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(noise),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(blue_noise),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&fog[1 - current]),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(noise_sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::Sampler(history_sampler),
+                },
+            ],
+        })
+    });
+    let composite_bind_groups = [0, 1].map(|current| {
+        tracker.record(ApiCategory::BindGroup, "create_bind_group");
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Fog Composite Bind Group"),
+            layout: composite_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&scene_depth),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&scene_color),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: wgpu::BindingResource::TextureView(&fog[current]),
+                },
+            ],
+        })
+    });
+    FrameTargets {
+        scene_color,
+        scene_depth,
+        fog,
+        fog_bind_groups,
+        composite_bind_groups,
+    }
+}
+
+/// Upload the tiling 3D noise the fog density is read from
+fn create_noise_texture(device: &wgpu::Device, queue: &wgpu::Queue) -> wgpu::TextureView {
+    let tracker = ApiCoverageTracker::global();
+    let size = wgpu::Extent3d {
+        width: NOISE_SIZE,
+        height: NOISE_SIZE,
+        depth_or_array_layers: NOISE_SIZE,
+    };
+    tracker.record(ApiCategory::Texture, "create_texture");
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Fog Noise Texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D3,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    tracker.record(ApiCategory::Queue, "write_texture");
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &noise_volume(NOISE_SIZE),
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(NOISE_SIZE),
+            rows_per_image: Some(NOISE_SIZE),
+        },
+        size,
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Upload the blue noise the ray offsets are read from
+///
+/// A texture that fails to load is replaced by a constant one, which turns
+/// the blue-noise jitter into a plain per-frame offset.
+fn create_blue_noise_texture(device: &wgpu::Device, queue: &wgpu::Queue) -> wgpu::TextureView {
+    let texels = blue_noise().unwrap_or_else(|e| {
+        log::warn!("{}", e);
+        vec![128; (BLUE_NOISE_SIZE * BLUE_NOISE_SIZE) as usize]
+    });
+    let tracker = ApiCoverageTracker::global();
+    let size = wgpu::Extent3d {
+        width: BLUE_NOISE_SIZE,
+        height: BLUE_NOISE_SIZE,
+        depth_or_array_layers: 1,
+    };
+    tracker.record(ApiCategory::Texture, "create_texture");
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Fog Blue Noise Texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    tracker.record(ApiCategory::Queue, "write_texture");
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &texels,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(BLUE_NOISE_SIZE),
+            rows_per_image: Some(BLUE_NOISE_SIZE),
+        },
+        size,
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(FOG_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+        assert!(FOG_SHADER.contains(&format!(
+            "const GOLDEN_RATIO_FRACTION: f32 = {};",
+            GOLDEN_RATIO_FRACTION
+        )));
+    }
+
+    #[test]
+    fn test_uniform_layout() {
+        assert_eq!(std::mem::size_of::<FogUniforms>(), 240);
+    }
+
+    #[test]
+    fn test_noise_volume_tiles() {
+        let size = 16;
+        let volume = noise_volume(size);
+        assert_eq!(volume.len(), (size * size * size) as usize);
+        let (min, max) = (volume.iter().min().unwrap(), volume.iter().max().unwrap());
+        assert!(max - min > 100, "noise spans {min}..{max}");
+
+        // Neighbours across each face differ no more than neighbours inside
+        let at = |x: u32, y: u32, z: u32| i32::from(volume[((z * size + y) * size + x) as usize]);
+        let max_step = (0..size)
+            .flat_map(|a| (0..size - 1).map(move |b| (a, b)))
+            .map(|(a, b)| (at(b, a, 0) - at(b + 1, a, 0)).abs())
+            .max()
+            .unwrap();
+        for a in 0..size {
+            for b in 0..size {
+                assert!((at(size - 1, a, b) - at(0, a, b)).abs() <= max_step + 8);
+                assert!((at(a, size - 1, b) - at(a, 0, b)).abs() <= max_step + 8);
+                assert!((at(a, b, size - 1) - at(a, b, 0)).abs() <= max_step + 8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_henyey_greenstein() {
+        // Integrates to one over the sphere for any anisotropy
+        for g in [-0.5, 0.0, 0.5, 0.8] {
+            let steps = 2000;
+            let integral: f32 = (0..steps)
+                .map(|i| {
+                    let cos_theta = -1.0 + 2.0 * (i as f32 + 0.5) / steps as f32;
+                    henyey_greenstein(cos_theta, g) * 2.0 * std::f32::consts::PI * 2.0
+                        / steps as f32
+                })
+                .sum();
+            assert!((integral - 1.0).abs() < 1e-2, "g = {g}: {integral}");
+        }
+        assert!(henyey_greenstein(1.0, 0.5) > henyey_greenstein(-1.0, 0.5));
+        assert!((henyey_greenstein(1.0, 0.0) - henyey_greenstein(-1.0, 0.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_jitter_offsets_spread() {
+        let mut offsets: Vec<f32> = (0..8).map(jitter_offset).collect();
+        assert!(offsets.iter().all(|o| (0.0..1.0).contains(o)));
+        offsets.sort_by(f32::total_cmp);
+        // No two of the first frames start close together
+        for pair in offsets.windows(2) {
+            assert!(pair[1] - pair[0] > 0.05, "{:?}", offsets);
+        }
+    }
+
+    #[test]
+    fn test_blue_noise_ranks_every_level_equally() {
+        let texels = blue_noise().expect("the built-in texture decodes");
+        assert_eq!(texels.len(), (BLUE_NOISE_SIZE * BLUE_NOISE_SIZE) as usize);
+        let mut histogram = [0u32; 256];
+        for &texel in &texels {
+            histogram[usize::from(texel)] += 1;
+        }
+        assert!(histogram.iter().all(|&count| count == 16));
+    }
+
+    #[test]
+    fn test_scene_mesh() {
+        let (vertices, indices) = scene_mesh();
+        assert_eq!(indices.len() % 3, 0);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+        // Pillars rise through the top of the fog
+        assert!(vertices.iter().any(|v| v.position[1] > FOG_HEIGHT));
+    }
+}
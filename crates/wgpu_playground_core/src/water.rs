@@ -0,0 +1,1088 @@
+//! Water with planar reflection and refraction
+//!
+//! Each frame draws the scene three times. The reflection pass mirrors the
+//! scene across the water plane and keeps what is above the surface; the
+//! refraction pass keeps what is below it. Both render to offscreen
+//! textures, and the main pass draws the scene once more and then the
+//! water surface, which samples the two textures at its own screen
+//! position. Two layers of a tiling normal map scroll across the surface,
+//! bending the normal that offsets those lookups, and the Fresnel term
+//! picks how much of each the surface shows: mostly the refracted floor
+//! when looking down, mostly the reflected sky at grazing angles.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::math_utils::{mat4_mul, reflection_y, Mat4};
+use crate::upload_belt;
+use bytemuck::Zeroable;
+
+/// Side of the square pool
+pub const WATER_SIZE: f32 = 16.0;
+
+/// Depth of the pool floor below the water surface
+pub const POOL_DEPTH: f32 = 1.5;
+
+/// Side of the normal map, in texels
+pub const NORMAL_MAP_SIZE: u32 = 256;
+
+/// Reflectance of water looking straight at the surface
+pub const F0: f32 = 0.02;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Sine waves summed into the normal map height field: texture-space
+/// frequency (whole periods per texture, so the map tiles), amplitude and
+/// phase
+const WAVES: [([f32; 2], f32, f32); 5] = [
+    ([1.0, 2.0], 0.5, 0.0),
+    ([3.0, -1.0], 0.3, 1.3),
+    ([-2.0, 5.0], 0.2, 2.1),
+    ([7.0, 3.0], 0.1, 0.4),
+    ([-5.0, -8.0], 0.06, 3.0),
+];
+
+/// Slope of the height field relative to the texture
+const WAVE_SLOPE: f32 = 0.04;
+
+pub const WATER_SHADER: &str = r#"// Water Example
+//
+// The scene is drawn three times: mirrored across the water plane into the
+// reflection texture, clipped below the surface into the refraction
+// texture, and as is into the frame. The water surface then samples both
+// textures at its screen position, offset by animated normal maps, and
+// blends them with the Fresnel term.
+
+struct PassUniforms {
+    view_proj: mat4x4<f32>,
+    // Fragments with dot(clip_plane.xyz, position) + clip_plane.w < 0 are
+    // discarded
+    clip_plane: vec4<f32>,
+};
+
+struct WaterUniforms {
+    eye: vec3<f32>,
+    time: f32,
+    wave_strength: f32,
+    water_size: f32,
+    // 0 combined, 1 reflection, 2 refraction, 3 Fresnel, 4 normals
+    view_mode: u32,
+    _padding: u32,
+};
+
+@group(0) @binding(0) var<uniform> camera: PassUniforms;
+
+@group(1) @binding(0) var<uniform> water: WaterUniforms;
+@group(1) @binding(1) var reflection_texture: texture_2d<f32>;
+@group(1) @binding(2) var refraction_texture: texture_2d<f32>;
+@group(1) @binding(3) var normal_map: texture_2d<f32>;
+@group(1) @binding(4) var screen_sampler: sampler;
+@group(1) @binding(5) var wave_sampler: sampler;
+
+// Direction towards the sun
+const SUN: vec3<f32> = vec3<f32>(0.408, 0.816, 0.408);
+const F0: f32 = 0.02;
+const WATER_TINT: vec3<f32> = vec3<f32>(0.55, 0.8, 0.85);
+// How far the waves shift the reflection and refraction lookups
+const DISTORTION: f32 = 0.03;
+
+struct SceneInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+struct SceneOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+@vertex
+fn vs_scene(in: SceneInput) -> SceneOutput {
+    var out: SceneOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.world_position = in.position;
+    out.normal = in.normal;
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_scene(in: SceneOutput) -> @location(0) vec4<f32> {
+    if dot(camera.clip_plane.xyz, in.world_position) + camera.clip_plane.w < 0.0 {
+        discard;
+    }
+    let light = 0.3 + 0.7 * max(dot(normalize(in.normal), SUN), 0.0);
+    return vec4<f32>(in.color * light, 1.0);
+}
+
+struct WaterOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) screen_position: vec4<f32>,
+};
+
+@vertex
+fn vs_water(@builtin(vertex_index) index: u32) -> WaterOutput {
+    // A triangle strip over the pool
+    let corner = vec2<f32>(f32(index & 1u), f32(index >> 1u)) * 2.0 - 1.0;
+    let position = vec3<f32>(corner.x, 0.0, corner.y) * (water.water_size * 0.5);
+    var out: WaterOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(position, 1.0);
+    out.world_position = position;
+    out.screen_position = out.clip_position;
+    return out;
+}
+
+// Surface normal from two layers of the normal map scrolling apart
+fn wave_normal(uv: vec2<f32>, time: f32) -> vec3<f32> {
+    let a = textureSample(normal_map, wave_sampler, uv + vec2<f32>(0.03, 0.02) * time).xyz;
+    let b = textureSample(normal_map, wave_sampler, uv * 1.7 + vec2<f32>(-0.025, 0.035) * time).xyz;
+    // The map stores tangent-space normals with z up; the water is y up
+    let slope = ((a.xy * 2.0 - 1.0) + (b.xy * 2.0 - 1.0)) * water.wave_strength;
+    return normalize(vec3<f32>(slope.x, 1.0, slope.y));
+}
+
+@fragment
+fn fs_water(in: WaterOutput) -> @location(0) vec4<f32> {
+    let normal = wave_normal(in.world_position.xz / 4.0, water.time);
+
+    // Both textures were rendered from this pixel's point of view
+    let ndc = in.screen_position.xy / in.screen_position.w;
+    let screen_uv = ndc * vec2<f32>(0.5, -0.5) + 0.5;
+    let offset = normal.xz * DISTORTION;
+    let reflection = textureSample(reflection_texture, screen_sampler, screen_uv + offset).rgb;
+    let refraction =
+        textureSample(refraction_texture, screen_sampler, screen_uv - offset).rgb * WATER_TINT;
+
+    // Schlick's approximation of the Fresnel reflectance
+    let view = normalize(water.eye - in.world_position);
+    let fresnel = F0 + (1.0 - F0) * pow(1.0 - max(dot(view, normal), 0.0), 5.0);
+    let specular = 0.5 * pow(max(dot(normal, normalize(view + SUN)), 0.0), 256.0);
+
+    switch water.view_mode {
+        case 1u: {
+            return vec4<f32>(reflection, 1.0);
+        }
+        case 2u: {
+            return vec4<f32>(refraction, 1.0);
+        }
+        case 3u: {
+            return vec4<f32>(vec3<f32>(fresnel), 1.0);
+        }
+        case 4u: {
+            return vec4<f32>(normal * 0.5 + 0.5, 1.0);
+        }
+        default: {}
+    }
+    return vec4<f32>(mix(refraction, reflection, fresnel) + vec3<f32>(specular), 1.0);
+}
+"#;
+
+/// What the water surface shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaterView {
+    /// Reflection and refraction blended by the Fresnel term
+    Combined,
+    Reflection,
+    Refraction,
+    /// The Fresnel term as gray levels
+    Fresnel,
+    /// The animated surface normals
+    Normals,
+}
+
+impl WaterView {
+    pub const ALL: [WaterView; 5] = [
+        WaterView::Combined,
+        WaterView::Reflection,
+        WaterView::Refraction,
+        WaterView::Fresnel,
+        WaterView::Normals,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WaterView::Combined => "Combined",
+            WaterView::Reflection => "Reflection",
+            WaterView::Refraction => "Refraction",
+            WaterView::Fresnel => "Fresnel",
+            WaterView::Normals => "Normals",
+        }
+    }
+
+    fn index(&self) -> u32 {
+        match self {
+            WaterView::Combined => 0,
+            WaterView::Reflection => 1,
+            WaterView::Refraction => 2,
+            WaterView::Fresnel => 3,
+            WaterView::Normals => 4,
+        }
+    }
+}
+
+/// Schlick's approximation of the share of light water reflects, for the
+/// cosine of the angle between the view direction and the surface normal
+pub fn fresnel(cos_theta: f32) -> f32 {
+    F0 + (1.0 - F0) * (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5)
+}
+
+/// Tangent-space normal of the wave height field at texture coordinates
+/// `(u, v)`; the field repeats every whole unit in both directions
+pub fn wave_normal(u: f32, v: f32) -> [f32; 3] {
+    let mut slope = [0.0, 0.0];
+    for ([fu, fv], amplitude, phase) in WAVES {
+        let angle = std::f32::consts::TAU * (fu * u + fv * v) + phase;
+        let derivative = amplitude * std::f32::consts::TAU * angle.cos();
+        slope[0] += derivative * fu;
+        slope[1] += derivative * fv;
+    }
+    crate::math_utils::normalize([-slope[0] * WAVE_SLOPE, -slope[1] * WAVE_SLOPE, 1.0])
+}
+
+/// RGBA8 mip chain of the normal map, largest level first
+///
+/// Each smaller level averages 2x2 texels of the one above, so distant
+/// waves fade to a flat surface instead of shimmering.
+pub fn normal_map_levels(size: u32) -> Vec<Vec<u8>> {
+    let mut level = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let normal = wave_normal(x as f32 / size as f32, y as f32 / size as f32);
+            level.extend(normal.map(|c| ((c * 0.5 + 0.5) * 255.0).round() as u8));
+            level.push(255);
+        }
+    }
+
+    let mut levels = vec![level];
+    let mut size = size as usize;
+    while size > 1 {
+        let half = size / 2;
+        let above = levels.last().unwrap();
+        let mut level = Vec::with_capacity(half * half * 4);
+        for y in 0..half {
+            for x in 0..half {
+                for channel in 0..4 {
+                    let texel = |dx, dy| above[((2 * y + dy) * size + 2 * x + dx) * 4 + channel];
+                    let sum: u32 = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                        .into_iter()
+                        .map(|(dx, dy)| u32::from(texel(dx, dy)))
+                        .sum();
+                    level.push(((sum + 2) / 4) as u8);
+                }
+            }
+        }
+        levels.push(level);
+        size = half;
+    }
+    levels
+}
+
+/// A vertex of the scene around the water
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SceneVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub color: [f32; 3],
+}
+
+/// Add an axis-aligned box spanning `min` to `max`, its faces wound
+/// counter-clockwise seen from outside
+pub(crate) fn add_box(
+    vertices: &mut Vec<SceneVertex>,
+    indices: &mut Vec<u16>,
+    min: [f32; 3],
+    max: [f32; 3],
+    color: [f32; 3],
+) {
+    // Per face: normal axis and sign; the corners follow from the bounds
+    for axis in 0..3 {
+        for sign in [-1.0f32, 1.0] {
+            let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+            let mut normal = [0.0; 3];
+            normal[axis] = sign;
+            let base = vertices.len() as u16;
+            // Going round the other way flips the face to point down the axis
+            let corners = if sign > 0.0 {
+                [(0, 0), (1, 0), (1, 1), (0, 1)]
+            } else {
+                [(0, 0), (0, 1), (1, 1), (1, 0)]
+            };
+            for (cu, cv) in corners {
+                let mut position = [0.0; 3];
+                position[axis] = if sign > 0.0 { max[axis] } else { min[axis] };
+                position[u] = if cu == 0 { min[u] } else { max[u] };
+                position[v] = if cv == 0 { min[v] } else { max[v] };
+                vertices.push(SceneVertex {
+                    position,
+                    normal,
+                    color,
+                });
+            }
+            indices.extend([0, 1, 2, 0, 2, 3].map(|i| base + i));
+        }
+    }
+}
+
+/// The pool around the water: a tiled floor, walls and pillars rising out
+/// of the water
+pub fn scene_mesh() -> (Vec<SceneVertex>, Vec<u16>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let half = WATER_SIZE / 2.0;
+
+    const TILES: u32 = 8;
+    let tile = WATER_SIZE / TILES as f32;
+    for z in 0..TILES {
+        for x in 0..TILES {
+            let color = if (x + z) % 2 == 0 {
+                [0.76, 0.62, 0.4]
+            } else {
+                [0.6, 0.48, 0.3]
+            };
+            let (x0, z0) = (-half + x as f32 * tile, -half + z as f32 * tile);
+            add_box(
+                &mut vertices,
+                &mut indices,
+                [x0, -POOL_DEPTH - 0.1, z0],
+                [x0 + tile, -POOL_DEPTH, z0 + tile],
+                color,
+            );
+        }
+    }
+
+    let wall = [0.55, 0.55, 0.6];
+    let (low, high, thickness) = (-POOL_DEPTH, 0.3, 0.4);
+    for (min, max) in [
+        (
+            [-half - thickness, low, -half - thickness],
+            [half + thickness, high, -half],
+        ),
+        (
+            [-half - thickness, low, half],
+            [half + thickness, high, half + thickness],
+        ),
+        ([-half - thickness, low, -half], [-half, high, half]),
+        ([half, low, -half], [half + thickness, high, half]),
+    ] {
+        add_box(&mut vertices, &mut indices, min, max, wall);
+    }
+
+    for ([x, z], height, color) in [
+        ([-3.0, -2.0], 1.5, [0.8, 0.25, 0.2]),
+        ([2.5, 1.5], 2.5, [0.9, 0.6, 0.15]),
+        ([0.5, -4.5], 1.0, [0.2, 0.65, 0.6]),
+        ([-4.5, 3.5], 2.0, [0.55, 0.3, 0.7]),
+    ] {
+        add_box(
+            &mut vertices,
+            &mut indices,
+            [x - 0.4, -POOL_DEPTH, z - 0.4],
+            [x + 0.4, height, z + 0.4],
+            color,
+        );
+    }
+    (vertices, indices)
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct PassUniforms {
+    view_proj: Mat4,
+    clip_plane: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct WaterUniforms {
+    eye: [f32; 3],
+    time: f32,
+    wave_strength: f32,
+    water_size: f32,
+    view_mode: u32,
+    _padding: u32,
+}
+
+/// Offscreen color and depth target of the reflection or refraction pass
+struct PassTarget {
+    color: wgpu::TextureView,
+    depth: wgpu::TextureView,
+}
+
+/// One camera's pass over the scene: its uniforms and their bind group
+struct ScenePass {
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Renderer of the water example
+pub struct WaterScene {
+    scene_pipeline: wgpu::RenderPipeline,
+    water_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    reflection_pass: ScenePass,
+    refraction_pass: ScenePass,
+    main_pass: ScenePass,
+    water_uniform_buffer: wgpu::Buffer,
+    water_layout: wgpu::BindGroupLayout,
+    water_bind_group: wgpu::BindGroup,
+    normal_map_view: wgpu::TextureView,
+    screen_sampler: wgpu::Sampler,
+    wave_sampler: wgpu::Sampler,
+    reflection_target: PassTarget,
+    refraction_target: PassTarget,
+    depth_view: wgpu::TextureView,
+    color_format: wgpu::TextureFormat,
+    /// Size of the frame and the offscreen scale the targets were made for
+    size: (u32, u32),
+    targets_half_resolution: bool,
+    /// How far the waves bend the surface, 0 for a mirror
+    pub wave_strength: f32,
+    pub view: WaterView,
+    /// Render the reflection and refraction at half the frame resolution
+    pub half_resolution: bool,
+}
+
+impl WaterScene {
+    /// Create the renderer drawing into `color_format` targets of `width`x`height`
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::Shader, "create_shader_module");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Water Shader"),
+            source: wgpu::ShaderSource::Wgsl(WATER_SHADER.into()),
+        });
+
+        let (vertices, indices) = scene_mesh();
+        let buffer = |label, contents: &[u8], usage| {
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: contents.len() as u64,
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            if !contents.is_empty() {
+                queue.write_buffer(&buffer, 0, contents);
+            }
+            buffer
+        };
+        let vertex_buffer = buffer(
+            "Water Scene Vertex Buffer",
+            bytemuck::cast_slice(&vertices),
+            wgpu::BufferUsages::VERTEX,
+        );
+        let index_buffer = buffer(
+            "Water Scene Index Buffer",
+            bytemuck::cast_slice(&indices),
+            wgpu::BufferUsages::INDEX,
+        );
+        let water_uniform_buffer = buffer(
+            "Water Uniform Buffer",
+            bytemuck::bytes_of(&WaterUniforms::zeroed()),
+            wgpu::BufferUsages::UNIFORM,
+        );
+
+        tracker.record(ApiCategory::BindGroup, "create_bind_group_layout");
+        let pass_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Water Pass Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let sampler_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        };
+        let water_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Water Surface Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                texture_entry(1),
+                texture_entry(2),
+                texture_entry(3),
+                sampler_entry(4),
+                sampler_entry(5),
+            ],
+        });
+
+        let scene_pass = |label| {
+            let uniform_buffer = buffer(
+                label,
+                bytemuck::bytes_of(&PassUniforms::zeroed()),
+                wgpu::BufferUsages::UNIFORM,
+            );
+            tracker.record(ApiCategory::BindGroup, "create_bind_group");
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &pass_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                }],
+            });
+            ScenePass {
+                uniform_buffer,
+                bind_group,
+            }
+        };
+        let reflection_pass = scene_pass("Water Reflection Pass Uniforms");
+        let refraction_pass = scene_pass("Water Refraction Pass Uniforms");
+        let main_pass = scene_pass("Water Main Pass Uniforms");
+
+        let normal_map_view = create_normal_map(device, queue);
+        tracker.record(ApiCategory::Sampler, "create_sampler");
+        let screen_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Water Screen Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let wave_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Water Wave Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::MipmapFilterMode::Linear,
+            ..Default::default()
+        });
+
+        tracker.record(ApiCategory::PipelineLayout, "create_pipeline_layout");
+        let scene_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Water Scene Pipeline Layout"),
+                bind_group_layouts: &[Some(&pass_layout)],
+                immediate_size: 0,
+            });
+        let water_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Water Surface Pipeline Layout"),
+                bind_group_layouts: &[Some(&pass_layout), Some(&water_layout)],
+                immediate_size: 0,
+            });
+
+        let vertex_attributes =
+            wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x3];
+        let scene_buffers = [wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<SceneVertex>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &vertex_attributes,
+        }];
+        let render_pipeline = |label, layout, entry_points: [&str; 2], buffers, topology| {
+            tracker.record(ApiCategory::RenderPipeline, "create_render_pipeline");
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(entry_points[0]),
+                    buffers,
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_points[1]),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                // The mirror flips the winding of the reflected scene
+                primitive: wgpu::PrimitiveState {
+                    topology,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: DEPTH_FORMAT,
+                    depth_write_enabled: Some(true),
+                    depth_compare: Some(wgpu::CompareFunction::Less),
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+            })
+        };
+        let scene_pipeline = render_pipeline(
+            "Water Scene Pipeline",
+            &scene_pipeline_layout,
+            ["vs_scene", "fs_scene"],
+            &scene_buffers,
+            wgpu::PrimitiveTopology::TriangleList,
+        );
+        let water_pipeline = render_pipeline(
+            "Water Surface Pipeline",
+            &water_pipeline_layout,
+            ["vs_water", "fs_water"],
+            &[],
+            wgpu::PrimitiveTopology::TriangleStrip,
+        );
+
+        let (reflection_target, refraction_target) =
+            create_pass_targets(device, color_format, offscreen_size(width, height, true));
+        let depth_view =
+            create_texture(device, "Water Depth Texture", DEPTH_FORMAT, (width, height));
+        let water_bind_group = create_water_bind_group(
+            device,
+            &water_layout,
+            &water_uniform_buffer,
+            [
+                &reflection_target.color,
+                &refraction_target.color,
+                &normal_map_view,
+            ],
+            [&screen_sampler, &wave_sampler],
+        );
+
+        Self {
+            scene_pipeline,
+            water_pipeline,
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+            reflection_pass,
+            refraction_pass,
+            main_pass,
+            water_uniform_buffer,
+            water_layout,
+            water_bind_group,
+            normal_map_view,
+            screen_sampler,
+            wave_sampler,
+            reflection_target,
+            refraction_target,
+            depth_view,
+            color_format,
+            size: (width, height),
+            targets_half_resolution: true,
+            wave_strength: 0.6,
+            view: WaterView::Combined,
+            half_resolution: true,
+        }
+    }
+
+    /// Size of the reflection and refraction textures
+    pub fn offscreen_size(&self) -> (u32, u32) {
+        offscreen_size(self.size.0, self.size.1, self.targets_half_resolution)
+    }
+
+    /// Update the camera and the wave time
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: Mat4, eye: [f32; 3], time: f32) {
+        // Reflected geometry above the water and refracted geometry below
+        // it, with a little overlap so the surface has no seam
+        let passes = [
+            (
+                &self.reflection_pass,
+                mat4_mul(&view_proj, &reflection_y(0.0)),
+                [0.0, 1.0, 0.0, 0.05],
+            ),
+            (&self.refraction_pass, view_proj, [0.0, -1.0, 0.0, 0.05]),
+            (&self.main_pass, view_proj, [0.0, 0.0, 0.0, 1.0]),
+        ];
+        for (pass, view_proj, clip_plane) in passes {
+            let uniforms = PassUniforms {
+                view_proj,
+                clip_plane,
+            };
+            upload_belt::write_buffer(
+                queue,
+                &pass.uniform_buffer,
+                0,
+                bytemuck::bytes_of(&uniforms),
+            );
+        }
+        let uniforms = WaterUniforms {
+            eye,
+            time,
+            wave_strength: self.wave_strength,
+            water_size: WATER_SIZE,
+            view_mode: self.view.index(),
+            _padding: 0,
+        };
+        upload_belt::write_buffer(
+            queue,
+            &self.water_uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+    }
+
+    /// Render the reflection and refraction passes, then the scene and the
+    /// water into `target`, a view of a texture of the given size
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        clear_color: wgpu::Color,
+    ) {
+        if self.size != (width, height) || self.targets_half_resolution != self.half_resolution {
+            self.resize(device, width, height);
+        }
+
+        let tracker = ApiCoverageTracker::global();
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Water Encoder"),
+        });
+        let passes = [
+            (
+                "Water Reflection Pass",
+                &self.reflection_pass,
+                &self.reflection_target.color,
+                &self.reflection_target.depth,
+            ),
+            (
+                "Water Refraction Pass",
+                &self.refraction_pass,
+                &self.refraction_target.color,
+                &self.refraction_target.depth,
+            ),
+            ("Water Main Pass", &self.main_pass, target, &self.depth_view),
+        ];
+        for (label, pass, color, depth) in passes {
+            tracker.record(ApiCategory::RenderPass, "begin_render_pass");
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: color,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: depth,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.scene_pipeline);
+            render_pass.set_bind_group(0, &pass.bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            tracker.record(ApiCategory::RenderPass, "draw_indexed");
+            render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+
+            // Only the frame gets the water, sampling the two textures
+            // rendered above
+            if std::ptr::eq(pass, &self.main_pass) {
+                render_pass.set_pipeline(&self.water_pipeline);
+                render_pass.set_bind_group(1, &self.water_bind_group, &[]);
+                tracker.record(ApiCategory::RenderPass, "draw");
+                render_pass.draw(0..4, 0..1);
+            }
+        }
+        upload_belt::submit(queue, std::iter::once(encoder.finish()));
+    }
+
+    /// Recreate the size-dependent targets and the bind group sampling them
+    fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        let offscreen = offscreen_size(width, height, self.half_resolution);
+        (self.reflection_target, self.refraction_target) =
+            create_pass_targets(device, self.color_format, offscreen);
+        self.depth_view =
+            create_texture(device, "Water Depth Texture", DEPTH_FORMAT, (width, height));
+        self.water_bind_group = create_water_bind_group(
+            device,
+            &self.water_layout,
+            &self.water_uniform_buffer,
+            [
+                &self.reflection_target.color,
+                &self.refraction_target.color,
+                &self.normal_map_view,
+            ],
+            [&self.screen_sampler, &self.wave_sampler],
+        );
+        self.size = (width, height);
+        self.targets_half_resolution = self.half_resolution;
+    }
+}
+
+/// Size of the reflection and refraction textures for a frame of `width`x`height`
+fn offscreen_size(width: u32, height: u32, half_resolution: bool) -> (u32, u32) {
+    let divisor = if half_resolution { 2 } else { 1 };
+    ((width / divisor).max(1), (height / divisor).max(1))
+}
+
+fn create_texture(
+    device: &wgpu::Device,
+    label: &str,
+    format: wgpu::TextureFormat,
+    (width, height): (u32, u32),
+) -> wgpu::TextureView {
+    ApiCoverageTracker::global().record(ApiCategory::Texture, "create_texture");
+    let usage = if format == DEPTH_FORMAT {
+        wgpu::TextureUsages::RENDER_ATTACHMENT
+    } else {
+        wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING
+    };
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Targets of the reflection and refraction passes
+fn create_pass_targets(
+    device: &wgpu::Device,
+    color_format: wgpu::TextureFormat,
+    size: (u32, u32),
+) -> (PassTarget, PassTarget) {
+    let target = |color_label, depth_label| PassTarget {
+        color: create_texture(device, color_label, color_format, size),
+        depth: create_texture(device, depth_label, DEPTH_FORMAT, size),
+    };
+    (
+        target("Water Reflection Texture", "Water Reflection Depth Texture"),
+        target("Water Refraction Texture", "Water Refraction Depth Texture"),
+    )
+}
+
+/// Upload the normal map with its mip chain
+fn create_normal_map(device: &wgpu::Device, queue: &wgpu::Queue) -> wgpu::TextureView {
+    let tracker = ApiCoverageTracker::global();
+    let levels = normal_map_levels(NORMAL_MAP_SIZE);
+    tracker.record(ApiCategory::Texture, "create_texture");
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Water Normal Map"),
+        size: wgpu::Extent3d {
+            width: NORMAL_MAP_SIZE,
+            height: NORMAL_MAP_SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: levels.len() as u32,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        // Normals are data, not colors
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    for (mip_level, data) in levels.iter().enumerate() {
+        let size = NORMAL_MAP_SIZE >> mip_level;
+        tracker.record(ApiCategory::Queue, "write_texture");
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: mip_level as u32,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(size * 4),
+                rows_per_image: Some(size),
+            },
+            wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn create_water_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    uniform_buffer: &wgpu::Buffer,
+    [reflection, refraction, normal_map]: [&wgpu::TextureView; 3],
+    [screen_sampler, wave_sampler]: [&wgpu::Sampler; 2],
+) -> wgpu::BindGroup {
+    ApiCoverageTracker::global().record(ApiCategory::BindGroup, "create_bind_group");
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Water Surface Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(reflection),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::TextureView(refraction),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(normal_map),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::Sampler(screen_sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 5,
+                resource: wgpu::BindingResource::Sampler(wave_sampler),
+            },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shader_is_valid() {
+        let module = naga::front::wgsl::parse_str(WATER_SHADER).expect("parses");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("validates");
+        assert!(WATER_SHADER.contains(&format!("const F0: f32 = {:?};", F0)));
+    }
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<SceneVertex>(), 36);
+        assert_eq!(std::mem::size_of::<PassUniforms>(), 80);
+        assert_eq!(std::mem::size_of::<WaterUniforms>(), 32);
+    }
+
+    #[test]
+    fn test_fresnel() {
+        assert!((fresnel(1.0) - F0).abs() < 1e-6);
+        assert!((fresnel(0.0) - 1.0).abs() < 1e-6);
+        // Grazing angles reflect more
+        assert!(fresnel(0.1) > fresnel(0.5));
+        assert!(fresnel(0.5) > fresnel(0.9));
+    }
+
+    #[test]
+    fn test_wave_normals_tile() {
+        for (u, v) in [(0.0, 0.0), (0.3, 0.7), (0.9, 0.15)] {
+            let normal = wave_normal(u, v);
+            let length = normal.iter().map(|c| c * c).sum::<f32>().sqrt();
+            assert!((length - 1.0).abs() < 1e-5);
+            assert!(normal[2] > 0.5, "normals point out of the surface");
+            let wrapped = wave_normal(u + 1.0, v - 1.0);
+            for (a, b) in normal.iter().zip(wrapped) {
+                assert!((a - b).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_map_levels() {
+        let levels = normal_map_levels(16);
+        assert_eq!(levels.len(), 5);
+        for (i, level) in levels.iter().enumerate() {
+            let size = 16 >> i;
+            assert_eq!(level.len(), size * size * 4);
+        }
+        // Averaging flattens the smallest level towards straight up
+        let top = &levels[4];
+        assert!(top[2] > 240);
+        assert!((i32::from(top[0]) - 128).abs() < 10);
+    }
+
+    #[test]
+    fn test_scene_mesh() {
+        let (vertices, indices) = scene_mesh();
+        assert_eq!(indices.len() % 3, 0);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+        // Pillars rise out of the water and the floor lies below it
+        assert!(vertices.iter().any(|v| v.position[1] > 1.0));
+        assert!(vertices
+            .iter()
+            .all(|v| v.position[1] >= -POOL_DEPTH - 0.1 - 1e-6));
+    }
+
+    #[test]
+    fn test_box_faces_wind_counter_clockwise_from_outside() {
+        let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+        add_box(
+            &mut vertices,
+            &mut indices,
+            [-1.0, -2.0, -3.0],
+            [1.0, 2.0, 3.0],
+            [1.0; 3],
+        );
+        assert_eq!(indices.len(), 36);
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| vertices[triangle[i] as usize]);
+            let edge = |p: SceneVertex| std::array::from_fn(|i| p.position[i] - a.position[i]);
+            let face_normal = crate::math_utils::cross(edge(b), edge(c));
+            assert!(crate::math_utils::dot(face_normal, a.normal) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_offscreen_size() {
+        assert_eq!(offscreen_size(640, 480, false), (640, 480));
+        assert_eq!(offscreen_size(640, 480, true), (320, 240));
+        assert_eq!(offscreen_size(1, 1, true), (1, 1));
+    }
+}
@@ -0,0 +1,1136 @@
+//! Workgroup size auto-tuning for compute shaders
+//!
+//! The best `@workgroup_size` for a compute shader depends on the adapter:
+//! subgroup width, register pressure and cache behaviour all change which
+//! size keeps the hardware busiest. [`tune`] dispatches a shader once per
+//! candidate size and times each configuration, then ranks them by
+//! throughput.
+//!
+//! Candidate sizes are applied in one of two ways, see [`SizeMethod`]. A
+//! shader whose workgroup size refers to `override` constants is compiled
+//! once and specialised per pipeline; any other shader has its
+//! `@workgroup_size` attribute rewritten and is recompiled per candidate.
+//!
+//! GPU time comes from timestamp queries when the device has
+//! [`wgpu::Features::TIMESTAMP_QUERY`]; otherwise each dispatch is timed on
+//! the CPU around submission and a blocking poll, which includes driver
+//! overhead but still ranks sizes sensibly for large dispatches.
+
+use crate::api_coverage::{ApiCategory, ApiCoverageTracker};
+use crate::shader_benchmark::TimingStats;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use naga::{AddressSpace, Expression, Module, ShaderStage};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Default number of timed dispatches per candidate
+pub const DEFAULT_ITERATIONS: u32 = 10;
+
+/// Upper bound on timed dispatches per candidate, to keep the UI responsive
+pub const MAX_ITERATIONS: u32 = 200;
+
+/// Default size of each storage buffer bound to the shader
+pub const DEFAULT_STORAGE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Smallest workgroup considered, below which lanes of a subgroup sit idle
+pub const MIN_WORKGROUP_INVOCATIONS: u32 = 16;
+
+/// Shader tuned when the user has not supplied one
+pub const DEFAULT_TUNER_SHADER: &str = r#"// Each invocation runs a short arithmetic loop over one vec4.
+// The workgroup size is an override, so the tuner specialises one module.
+override block_size: u32 = 64u;
+
+@group(0) @binding(0) var<storage, read_write> data: array<vec4<f32>>;
+
+@compute @workgroup_size(block_size)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= arrayLength(&data)) {
+        return;
+    }
+    var value = data[i];
+    for (var k = 0u; k < 64u; k = k + 1u) {
+        value = value * 1.0001 + vec4<f32>(0.5);
+    }
+    data[i] = value;
+}
+"#;
+
+/// Errors that stop a tuning run before any candidate is measured
+#[derive(Debug, Clone, PartialEq)]
+pub enum TunerError {
+    /// naga rejected the source
+    InvalidShader(String),
+    /// No compute entry point, or none with the requested name
+    NoEntryPoint(String),
+    /// The shader binds a resource the tuner cannot synthesise
+    UnsupportedBinding { group: u32, binding: u32 },
+    /// The `@workgroup_size` attribute of the entry point was not found
+    MissingAttribute(String),
+    /// No workgroup size fits the invocation grid and device limits
+    NoCandidates,
+    /// The device rejected the shader module
+    Device(String),
+}
+
+impl fmt::Display for TunerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TunerError::InvalidShader(error) => write!(f, "Invalid shader: {}", error),
+            TunerError::NoEntryPoint(name) if name.is_empty() => {
+                write!(f, "The shader has no compute entry point")
+            }
+            TunerError::NoEntryPoint(name) => {
+                write!(f, "No compute entry point named '{}'", name)
+            }
+            TunerError::UnsupportedBinding { group, binding } => write!(
+                f,
+                "Binding @group({}) @binding({}) is not a uniform or storage buffer",
+                group, binding
+            ),
+            TunerError::MissingAttribute(name) => {
+                write!(f, "Could not find @workgroup_size on fn {}", name)
+            }
+            TunerError::NoCandidates => {
+                write!(
+                    f,
+                    "No workgroup size fits the invocation grid and device limits"
+                )
+            }
+            TunerError::Device(error) => write!(f, "Device rejected the shader: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for TunerError {}
+
+/// How a candidate workgroup size is applied to the shader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SizeMethod {
+    /// Set pipeline-overridable constants; one key per tunable dimension
+    Overrides { keys: [Option<String>; 3] },
+    /// Rewrite the `@workgroup_size` attribute and recompile
+    Codegen,
+}
+
+impl SizeMethod {
+    /// Short description for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            SizeMethod::Overrides { .. } => "Override constants",
+            SizeMethod::Codegen => "Source rewrite",
+        }
+    }
+}
+
+/// A buffer the tuner creates for one of the shader's bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferBinding {
+    pub group: u32,
+    pub binding: u32,
+    /// Uniform rather than storage
+    pub uniform: bool,
+    /// Size of the bound type; the element stride for runtime-sized arrays
+    pub min_size: u64,
+    /// Ends in a runtime-sized array
+    pub runtime_sized: bool,
+}
+
+/// What the tuner learnt about a shader's entry point
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderAnalysis {
+    pub entry_point: String,
+    pub method: SizeMethod,
+    /// Workgroup size in the source; 1 where an override is used
+    pub declared_size: [u32; 3],
+    pub bindings: Vec<BufferBinding>,
+}
+
+impl ShaderAnalysis {
+    /// Dimensions the tuner may change
+    pub fn tunable(&self) -> [bool; 3] {
+        match &self.method {
+            SizeMethod::Overrides { keys } => [0, 1, 2].map(|d| keys[d].is_some()),
+            SizeMethod::Codegen => [true; 3],
+        }
+    }
+}
+
+/// Settings for a tuning run
+#[derive(Debug, Clone, PartialEq)]
+pub struct TunerConfig {
+    /// Compute entry point; empty picks the first one
+    pub entry_point: String,
+    /// Total invocations to cover in each dimension
+    pub invocations: [u32; 3],
+    /// Timed dispatches per candidate, after one warm-up dispatch
+    pub iterations: u32,
+    /// Size of storage buffers that end in a runtime-sized array
+    pub storage_size: u64,
+}
+
+impl Default for TunerConfig {
+    fn default() -> Self {
+        Self {
+            entry_point: String::new(),
+            invocations: [(DEFAULT_STORAGE_SIZE / 16) as u32, 1, 1],
+            iterations: DEFAULT_ITERATIONS,
+            storage_size: DEFAULT_STORAGE_SIZE,
+        }
+    }
+}
+
+/// Where the measured durations come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingSource {
+    /// GPU timestamps written at the start and end of each compute pass
+    Timestamps,
+    /// CPU wall clock around submission and a blocking poll
+    WallClock,
+}
+
+impl TimingSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimingSource::Timestamps => "GPU timestamp queries",
+            TimingSource::WallClock => "CPU wall clock",
+        }
+    }
+}
+
+/// Measurements for one workgroup size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TuneResult {
+    pub size: [u32; 3],
+    pub workgroups: [u32; 3],
+    pub timing: TimingStats,
+    /// Requested invocations per second, from the median time
+    pub throughput: f64,
+}
+
+impl TuneResult {
+    /// Invocations in one workgroup
+    pub fn workgroup_invocations(&self) -> u32 {
+        self.size.iter().product()
+    }
+}
+
+/// A candidate that could not be measured
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuneFailure {
+    pub size: [u32; 3],
+    pub error: String,
+}
+
+/// Column to order results by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Size,
+    Invocations,
+    Workgroups,
+    Median,
+    Min,
+    StdDev,
+    Throughput,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 7] = [
+        SortColumn::Size,
+        SortColumn::Invocations,
+        SortColumn::Workgroups,
+        SortColumn::Median,
+        SortColumn::Min,
+        SortColumn::StdDev,
+        SortColumn::Throughput,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortColumn::Size => "Size",
+            SortColumn::Invocations => "Invocations",
+            SortColumn::Workgroups => "Workgroups",
+            SortColumn::Median => "Median",
+            SortColumn::Min => "Min",
+            SortColumn::StdDev => "Std dev",
+            SortColumn::Throughput => "Throughput",
+        }
+    }
+}
+
+/// Outcome of a tuning run
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuneReport {
+    pub analysis: ShaderAnalysis,
+    pub timing_source: TimingSource,
+    pub invocations: [u32; 3],
+    pub iterations: u32,
+    pub results: Vec<TuneResult>,
+    pub failures: Vec<TuneFailure>,
+}
+
+impl TuneReport {
+    /// The size with the highest throughput
+    pub fn best(&self) -> Option<&TuneResult> {
+        self.results
+            .iter()
+            .max_by(|a, b| a.throughput.total_cmp(&b.throughput))
+    }
+
+    /// Attribute to paste into the shader for the best size
+    pub fn recommendation(&self) -> Option<String> {
+        self.best().map(|best| format_attribute(best.size))
+    }
+
+    /// Order the results by a column
+    pub fn sort(&mut self, column: SortColumn, ascending: bool) {
+        sort_results(&mut self.results, column, ascending);
+    }
+}
+
+/// Order results by a column; ties keep their previous order
+pub fn sort_results(results: &mut [TuneResult], column: SortColumn, ascending: bool) {
+    results.sort_by(|a, b| {
+        let ordering = match column {
+            SortColumn::Size => a.size.cmp(&b.size),
+            SortColumn::Invocations => a.workgroup_invocations().cmp(&b.workgroup_invocations()),
+            SortColumn::Workgroups => {
+                let count =
+                    |r: &TuneResult| r.workgroups.iter().map(|&w| w as u64).product::<u64>();
+                count(a).cmp(&count(b))
+            }
+            SortColumn::Median => a.timing.median.cmp(&b.timing.median),
+            SortColumn::Min => a.timing.min.cmp(&b.timing.min),
+            SortColumn::StdDev => a.timing.std_dev.cmp(&b.timing.std_dev),
+            SortColumn::Throughput => a.throughput.total_cmp(&b.throughput),
+        };
+        if ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+}
+
+/// `@workgroup_size(...)` with trailing dimensions of 1 omitted
+pub fn format_attribute(size: [u32; 3]) -> String {
+    let used = if size[2] > 1 {
+        3
+    } else if size[1] > 1 {
+        2
+    } else {
+        1
+    };
+    let dims: Vec<String> = size[..used].iter().map(u32::to_string).collect();
+    format!("@workgroup_size({})", dims.join(", "))
+}
+
+/// Format invocations per second for display
+pub fn format_throughput(throughput: f64) -> String {
+    if throughput >= 1e9 {
+        format!("{:.2} G inv/s", throughput / 1e9)
+    } else if throughput >= 1e6 {
+        format!("{:.2} M inv/s", throughput / 1e6)
+    } else {
+        format!("{:.0} inv/s", throughput)
+    }
+}
+
+/// Features the tuner can use when the adapter has them
+///
+/// Only timestamp queries are requested; without them timing falls back to
+/// the CPU clock.
+pub fn required_features(adapter: &wgpu::Adapter) -> wgpu::Features {
+    adapter.features() & wgpu::Features::TIMESTAMP_QUERY
+}
+
+/// The timing source a device will use
+pub fn timing_source(device: &wgpu::Device) -> TimingSource {
+    if device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+        TimingSource::Timestamps
+    } else {
+        TimingSource::WallClock
+    }
+}
+
+fn parse(source: &str) -> Result<(Module, naga::valid::ModuleInfo), TunerError> {
+    let module = naga::front::wgsl::parse_str(source)
+        .map_err(|e| TunerError::InvalidShader(e.emit_to_string(source)))?;
+    let info = Validator::new(ValidationFlags::all(), Capabilities::default())
+        .validate(&module)
+        .map_err(|e| TunerError::InvalidShader(e.emit_to_string(source)))?;
+    Ok((module, info))
+}
+
+/// Find the entry point, how its workgroup size can be changed and the
+/// buffers it needs
+pub fn analyze(source: &str, entry_point: &str) -> Result<ShaderAnalysis, TunerError> {
+    let (module, info) = parse(source)?;
+    let (index, entry) = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .find(|(_, ep)| {
+            ep.stage == ShaderStage::Compute && (entry_point.is_empty() || ep.name == entry_point)
+        })
+        .ok_or_else(|| TunerError::NoEntryPoint(entry_point.to_string()))?;
+
+    // Overrides are used when every overridden dimension names a constant
+    // directly; an expression such as `size * 2` needs the source rewritten
+    let method = match entry.workgroup_size_overrides {
+        Some(overrides) => {
+            let keys = overrides.map(|expression| match module.global_expressions[expression?] {
+                Expression::Override(handle) => {
+                    let constant = &module.overrides[handle];
+                    // wgpu matches on the @id when one is given, else the name
+                    match constant.id {
+                        Some(id) => Some(id.to_string()),
+                        None => constant.name.clone(),
+                    }
+                }
+                _ => None,
+            });
+            let direct = (0..3).all(|d| overrides[d].is_none() || keys[d].is_some());
+            if direct {
+                SizeMethod::Overrides { keys }
+            } else {
+                SizeMethod::Codegen
+            }
+        }
+        None => SizeMethod::Codegen,
+    };
+
+    let usage = info.get_entry_point(index);
+    let mut bindings = Vec::new();
+    for (handle, var) in module.global_variables.iter() {
+        let Some(resource) = &var.binding else {
+            continue;
+        };
+        if usage[handle].is_empty() {
+            continue;
+        }
+        let uniform = match var.space {
+            AddressSpace::Uniform => true,
+            AddressSpace::Storage { .. } => false,
+            _ => {
+                return Err(TunerError::UnsupportedBinding {
+                    group: resource.group,
+                    binding: resource.binding,
+                })
+            }
+        };
+        let inner = &module.types[var.ty].inner;
+        let runtime_sized = match inner {
+            naga::TypeInner::Array { size, .. } => *size == naga::ArraySize::Dynamic,
+            naga::TypeInner::Struct { members, .. } => members.last().is_some_and(|member| {
+                matches!(
+                    module.types[member.ty].inner,
+                    naga::TypeInner::Array {
+                        size: naga::ArraySize::Dynamic,
+                        ..
+                    }
+                )
+            }),
+            _ => false,
+        };
+        bindings.push(BufferBinding {
+            group: resource.group,
+            binding: resource.binding,
+            uniform,
+            min_size: inner.size(module.to_ctx()) as u64,
+            runtime_sized,
+        });
+    }
+    bindings.sort_by_key(|b| (b.group, b.binding));
+
+    Ok(ShaderAnalysis {
+        entry_point: entry.name.clone(),
+        method,
+        declared_size: entry.workgroup_size,
+        bindings,
+    })
+}
+
+/// Replace the `@workgroup_size` attribute of `fn entry_point`
+pub fn with_workgroup_size(
+    source: &str,
+    entry_point: &str,
+    size: [u32; 3],
+) -> Result<String, TunerError> {
+    let missing = || TunerError::MissingAttribute(entry_point.to_string());
+    let function = find_function(source, entry_point).ok_or_else(missing)?;
+    // The attribute belongs to this function if no other function sits between
+    let previous_fn = source[..function].rfind("fn ").map_or(0, |i| i + 3);
+    let start = source[previous_fn..function]
+        .rfind("@workgroup_size")
+        .map(|i| previous_fn + i)
+        .ok_or_else(missing)?;
+    let open = start + source[start..].find('(').ok_or_else(missing)?;
+    let mut depth = 0;
+    let mut end = None;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(open + i + 1);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let end = end.ok_or_else(missing)?;
+    Ok(format!(
+        "{}@workgroup_size({}, {}, {}){}",
+        &source[..start],
+        size[0],
+        size[1],
+        size[2],
+        &source[end..]
+    ))
+}
+
+/// Byte offset of `fn <name>` followed by its parameter list
+fn find_function(source: &str, name: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(i) = source[offset..].find("fn ") {
+        let at = offset + i;
+        offset = at + 3;
+        let preceded_by_ident = source[..at]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let rest = source[offset..].trim_start();
+        if !preceded_by_ident
+            && rest.starts_with(name)
+            && rest[name.len()..].trim_start().starts_with('(')
+        {
+            return Some(at);
+        }
+    }
+    None
+}
+
+/// Workgroup sizes worth measuring for an invocation grid
+///
+/// Tunable dimensions with more than one invocation take powers of two up to
+/// the device limit and the grid extent; other dimensions keep `fixed`, or 1
+/// when tunable. Sizes whose total falls outside
+/// [`MIN_WORKGROUP_INVOCATIONS`] (or the whole grid, if smaller) and the
+/// per-workgroup invocation limit are dropped, as are sizes that need more
+/// workgroups than a dispatch allows.
+pub fn candidate_sizes(
+    invocations: [u32; 3],
+    tunable: [bool; 3],
+    fixed: [u32; 3],
+    limits: &wgpu::Limits,
+) -> Vec<[u32; 3]> {
+    let dim_limits = [
+        limits.max_compute_workgroup_size_x,
+        limits.max_compute_workgroup_size_y,
+        limits.max_compute_workgroup_size_z,
+    ];
+    let options: [Vec<u32>; 3] = [0, 1, 2].map(|d| {
+        if !tunable[d] {
+            return vec![fixed[d].max(1)];
+        }
+        if invocations[d] <= 1 {
+            return vec![1];
+        }
+        let cap = dim_limits[d].min(
+            invocations[d]
+                .checked_next_power_of_two()
+                .unwrap_or(u32::MAX),
+        );
+        std::iter::successors(Some(1u32), |&s| s.checked_mul(2))
+            .take_while(|&s| s <= cap)
+            .collect()
+    });
+    let largest: u64 = options
+        .iter()
+        .map(|o| *o.iter().max().unwrap_or(&1) as u64)
+        .product();
+    let minimum = (MIN_WORKGROUP_INVOCATIONS as u64).min(largest);
+
+    let mut sizes = Vec::new();
+    for &x in &options[0] {
+        for &y in &options[1] {
+            for &z in &options[2] {
+                let size = [x, y, z];
+                let total = size.iter().map(|&s| s as u64).product::<u64>();
+                let fits = total >= minimum
+                    && total <= limits.max_compute_invocations_per_workgroup as u64
+                    && (0..3).all(|d| {
+                        workgroup_count(invocations[d], size[d])
+                            <= limits.max_compute_workgroups_per_dimension
+                    });
+                if fits {
+                    sizes.push(size);
+                }
+            }
+        }
+    }
+    sizes.sort_by_key(|s| (s.iter().product::<u32>(), std::cmp::Reverse(s[0])));
+    sizes
+}
+
+/// Workgroups needed to cover `invocations` with groups of `size`
+pub fn workgroup_count(invocations: u32, size: u32) -> u32 {
+    invocations.max(1).div_ceil(size.max(1))
+}
+
+/// Measure every candidate workgroup size for the shader's entry point
+pub fn tune(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    source: &str,
+    config: &TunerConfig,
+) -> Result<TuneReport, TunerError> {
+    let analysis = analyze(source, &config.entry_point)?;
+    let candidates = candidate_sizes(
+        config.invocations,
+        analysis.tunable(),
+        analysis.declared_size,
+        &device.limits(),
+    );
+    if candidates.is_empty() {
+        return Err(TunerError::NoCandidates);
+    }
+    let iterations = config.iterations.clamp(1, MAX_ITERATIONS);
+    let timing_source = timing_source(device);
+    let tracker = ApiCoverageTracker::global();
+
+    // Buffers and bind groups are shared by every candidate; with an
+    // automatic layout each pipeline needs its own bind group though
+    let buffers: Vec<wgpu::Buffer> = analysis
+        .bindings
+        .iter()
+        .map(|binding| {
+            let size = if binding.runtime_sized {
+                config.storage_size.max(binding.min_size)
+            } else {
+                binding.min_size
+            };
+            tracker.record(ApiCategory::Buffer, "create_buffer");
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Workgroup Tuner Buffer"),
+                size: size.max(16).next_multiple_of(16),
+                usage: if binding.uniform {
+                    wgpu::BufferUsages::UNIFORM
+                } else {
+                    wgpu::BufferUsages::STORAGE
+                },
+                mapped_at_creation: false,
+            })
+        })
+        .collect();
+
+    let shared_module = match analysis.method {
+        SizeMethod::Overrides { .. } => Some(create_module(device, source)?),
+        SizeMethod::Codegen => None,
+    };
+
+    let mut results = Vec::new();
+    let mut failures = Vec::new();
+    for size in candidates {
+        let pipeline = match &shared_module {
+            Some(module) => create_pipeline(device, module, &analysis, size),
+            None => with_workgroup_size(source, &analysis.entry_point, size)
+                .and_then(|source| create_module(device, &source))
+                .map_err(|e| e.to_string())
+                .and_then(|module| create_pipeline(device, &module, &analysis, size)),
+        };
+        let pipeline = match pipeline {
+            Ok(pipeline) => pipeline,
+            Err(error) => {
+                failures.push(TuneFailure { size, error });
+                continue;
+            }
+        };
+        let bind_groups = bind_groups(device, &pipeline, &analysis.bindings, &buffers);
+        let workgroups = [0, 1, 2].map(|d| workgroup_count(config.invocations[d], size[d]));
+        let dispatch = Dispatch {
+            pipeline: &pipeline,
+            bind_groups: &bind_groups,
+            workgroups,
+        };
+
+        // Warm-up: first use of a pipeline can include lazy driver work
+        dispatch.time_wall_clock(device, queue, 1);
+        let samples = match timing_source {
+            TimingSource::Timestamps => dispatch.time_timestamps(device, queue, iterations),
+            TimingSource::WallClock => Ok(dispatch.time_wall_clock(device, queue, iterations)),
+        };
+        let samples = match samples {
+            Ok(samples) => samples,
+            Err(error) => {
+                failures.push(TuneFailure { size, error });
+                continue;
+            }
+        };
+        let timing = TimingStats::from_samples(&samples).expect("at least one iteration");
+        let requested: f64 = config
+            .invocations
+            .iter()
+            .map(|&i| i.max(1) as f64)
+            .product();
+        let seconds = timing.median.as_secs_f64().max(1e-9);
+        results.push(TuneResult {
+            size,
+            workgroups,
+            timing,
+            throughput: requested / seconds,
+        });
+    }
+
+    Ok(TuneReport {
+        analysis,
+        timing_source,
+        invocations: config.invocations,
+        iterations,
+        results,
+        failures,
+    })
+}
+
+fn create_module(device: &wgpu::Device, source: &str) -> Result<wgpu::ShaderModule, TunerError> {
+    let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+    ApiCoverageTracker::global().record(ApiCategory::Shader, "create_shader_module");
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Workgroup Tuner Shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    // Web devices report errors asynchronously, so they are not waited for
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(error) = pollster::block_on(scope.pop()) {
+        return Err(TunerError::Device(error.to_string()));
+    }
+    #[cfg(target_arch = "wasm32")]
+    let _ = scope;
+    Ok(module)
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    module: &wgpu::ShaderModule,
+    analysis: &ShaderAnalysis,
+    size: [u32; 3],
+) -> Result<wgpu::ComputePipeline, String> {
+    let constants: Vec<(&str, f64)> = match &analysis.method {
+        SizeMethod::Overrides { keys } => (0..3)
+            .filter_map(|d| keys[d].as_deref().map(|key| (key, size[d] as f64)))
+            .collect(),
+        SizeMethod::Codegen => Vec::new(),
+    };
+    let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+    ApiCoverageTracker::global().record(ApiCategory::ComputePipeline, "create_compute_pipeline");
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("Workgroup Tuner Pipeline"),
+        layout: None,
+        module,
+        entry_point: Some(&analysis.entry_point),
+        compilation_options: wgpu::PipelineCompilationOptions {
+            constants: &constants,
+            ..Default::default()
+        },
+        cache: crate::device_pipeline_cache::for_device(device).as_ref(),
+    });
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(error) = pollster::block_on(scope.pop()) {
+        return Err(error.to_string());
+    }
+    #[cfg(target_arch = "wasm32")]
+    let _ = scope;
+    Ok(pipeline)
+}
+
+fn bind_groups(
+    device: &wgpu::Device,
+    pipeline: &wgpu::ComputePipeline,
+    bindings: &[BufferBinding],
+    buffers: &[wgpu::Buffer],
+) -> Vec<(u32, wgpu::BindGroup)> {
+    let tracker = ApiCoverageTracker::global();
+    let mut groups: Vec<u32> = bindings.iter().map(|b| b.group).collect();
+    groups.dedup();
+    groups
+        .into_iter()
+        .map(|group| {
+            let entries: Vec<wgpu::BindGroupEntry> = bindings
+                .iter()
+                .zip(buffers)
+                .filter(|(binding, _)| binding.group == group)
+                .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                    binding: binding.binding,
+                    resource: buffer.as_entire_binding(),
+                })
+                .collect();
+            tracker.record(ApiCategory::BindGroup, "create_bind_group");
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Workgroup Tuner Bind Group"),
+                layout: &pipeline.get_bind_group_layout(group),
+                entries: &entries,
+            });
+            (group, bind_group)
+        })
+        .collect()
+}
+
+struct Dispatch<'a> {
+    pipeline: &'a wgpu::ComputePipeline,
+    bind_groups: &'a [(u32, wgpu::BindGroup)],
+    workgroups: [u32; 3],
+}
+
+impl Dispatch<'_> {
+    fn record(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        timestamps: Option<(&wgpu::QuerySet, u32)>,
+    ) {
+        let tracker = ApiCoverageTracker::global();
+        tracker.record(ApiCategory::CommandEncoder, "begin_compute_pass");
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Workgroup Tuner Pass"),
+            timestamp_writes: timestamps.map(|(query_set, index)| {
+                wgpu::ComputePassTimestampWrites {
+                    query_set,
+                    beginning_of_pass_write_index: Some(index),
+                    end_of_pass_write_index: Some(index + 1),
+                }
+            }),
+        });
+        tracker.record(ApiCategory::ComputePass, "set_pipeline");
+        pass.set_pipeline(self.pipeline);
+        for (group, bind_group) in self.bind_groups {
+            tracker.record(ApiCategory::ComputePass, "set_bind_group");
+            pass.set_bind_group(*group, bind_group, &[]);
+        }
+        tracker.record(ApiCategory::ComputePass, "dispatch_workgroups");
+        let [x, y, z] = self.workgroups;
+        pass.dispatch_workgroups(x, y, z);
+    }
+
+    /// One submission per dispatch, each waited on
+    fn time_wall_clock(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        iterations: u32,
+    ) -> Vec<Duration> {
+        let tracker = ApiCoverageTracker::global();
+        (0..iterations)
+            .map(|_| {
+                tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+                let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Workgroup Tuner Encoder"),
+                });
+                self.record(&mut encoder, None);
+                let start = Instant::now();
+                tracker.record(ApiCategory::Queue, "submit");
+                queue.submit(std::iter::once(encoder.finish()));
+                let _ = device.poll(wgpu::PollType::wait_indefinitely());
+                start.elapsed()
+            })
+            .collect()
+    }
+
+    /// All dispatches in one submission, each pass bracketed by timestamps
+    fn time_timestamps(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        iterations: u32,
+    ) -> Result<Vec<Duration>, String> {
+        let tracker = ApiCoverageTracker::global();
+        let count = iterations * 2;
+        let bytes = count as u64 * 8;
+        tracker.record(ApiCategory::QuerySet, "create_query_set");
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Workgroup Tuner Timestamps"),
+            ty: wgpu::QueryType::Timestamp,
+            count,
+        });
+        tracker.record(ApiCategory::Buffer, "create_buffer");
+        let resolve = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Workgroup Tuner Resolve Buffer"),
+            size: bytes,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Workgroup Tuner Readback Buffer"),
+            size: bytes,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        tracker.record(ApiCategory::CommandEncoder, "create_command_encoder");
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Workgroup Tuner Encoder"),
+        });
+        for i in 0..iterations {
+            self.record(&mut encoder, Some((&query_set, i * 2)));
+        }
+        tracker.record(ApiCategory::CommandEncoder, "resolve_query_set");
+        encoder.resolve_query_set(&query_set, 0..count, &resolve, 0);
+        tracker.record(ApiCategory::CommandEncoder, "copy_buffer_to_buffer");
+        encoder.copy_buffer_to_buffer(&resolve, 0, &staging, 0, bytes);
+        tracker.record(ApiCategory::Queue, "submit");
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        tracker.record(ApiCategory::Buffer, "map_async");
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+        match receiver.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(format!("Failed to read timestamps: {}", e)),
+            Err(_) => return Err("Failed to read timestamps".to_string()),
+        }
+        let ticks: Vec<u64> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        staging.unmap();
+        Ok(durations_from_timestamps(
+            &ticks,
+            queue.get_timestamp_period(),
+        ))
+    }
+}
+
+/// Convert begin/end timestamp pairs to durations
+///
+/// `period` is nanoseconds per tick. Pairs that run backwards, which some
+/// drivers report when a pass is too short to measure, count as zero.
+pub fn durations_from_timestamps(ticks: &[u64], period: f32) -> Vec<Duration> {
+    ticks
+        .chunks_exact(2)
+        .map(|pair| {
+            let elapsed = pair[1].saturating_sub(pair[0]);
+            Duration::from_nanos((elapsed as f64 * period as f64).round() as u64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(size: [u32; 3], median_us: u64, throughput: f64) -> TuneResult {
+        let median = Duration::from_micros(median_us);
+        TuneResult {
+            size,
+            workgroups: [1024 / size[0], 1, 1],
+            timing: TimingStats::from_samples(&[median]).unwrap(),
+            throughput,
+        }
+    }
+
+    #[test]
+    fn test_default_shader_uses_overrides() {
+        let analysis = analyze(DEFAULT_TUNER_SHADER, "").unwrap();
+        assert_eq!(analysis.entry_point, "main");
+        assert_eq!(
+            analysis.method,
+            SizeMethod::Overrides {
+                keys: [Some("block_size".to_string()), None, None]
+            }
+        );
+        assert_eq!(analysis.tunable(), [true, false, false]);
+        assert_eq!(analysis.declared_size[1..], [1, 1]);
+        assert_eq!(analysis.bindings.len(), 1);
+        let binding = analysis.bindings[0];
+        assert!(!binding.uniform);
+        assert!(binding.runtime_sized);
+        assert_eq!(binding.min_size, 16);
+    }
+
+    #[test]
+    fn test_analyze_literal_size_and_override_ids() {
+        let literal = r#"
+struct Params { scale: f32, count: u32 }
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> out: array<f32>;
+@group(1) @binding(0) var<storage, read> unused: array<f32>;
+
+@compute @workgroup_size(8, 8)
+fn blur(@builtin(global_invocation_id) id: vec3<u32>) {
+    out[id.x] = params.scale * f32(params.count);
+}
+"#;
+        let analysis = analyze(literal, "blur").unwrap();
+        assert_eq!(analysis.method, SizeMethod::Codegen);
+        assert_eq!(analysis.declared_size, [8, 8, 1]);
+        // The unused binding is left out of the automatic layout
+        assert_eq!(analysis.bindings.len(), 2);
+        assert!(analysis.bindings[0].uniform);
+        assert_eq!(analysis.bindings[0].min_size, 8);
+
+        let with_id = r#"
+@id(7) override wx: u32 = 8u;
+@compute @workgroup_size(wx, 4)
+fn main() {}
+"#;
+        let analysis = analyze(with_id, "").unwrap();
+        assert_eq!(
+            analysis.method,
+            SizeMethod::Overrides {
+                keys: [Some("7".to_string()), None, None]
+            }
+        );
+        assert_eq!(analysis.declared_size[1], 4);
+
+        assert_eq!(
+            analyze(literal, "missing"),
+            Err(TunerError::NoEntryPoint("missing".to_string()))
+        );
+        assert!(matches!(
+            analyze("fn broken(", ""),
+            Err(TunerError::InvalidShader(_))
+        ));
+    }
+
+    #[test]
+    fn test_textures_are_rejected() {
+        let source = r#"
+@group(0) @binding(2) var image: texture_storage_2d<rgba8unorm, write>;
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    textureStore(image, vec2<i32>(id.xy), vec4<f32>(1.0));
+}
+"#;
+        assert_eq!(
+            analyze(source, ""),
+            Err(TunerError::UnsupportedBinding {
+                group: 0,
+                binding: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_rewrite_workgroup_size() {
+        let source = r#"
+@compute @workgroup_size(8, 8)
+fn first() {}
+
+@compute
+@workgroup_size(max(1, 2))
+fn second() {}
+
+fn helper_second() {}
+"#;
+        let rewritten = with_workgroup_size(source, "second", [64, 1, 1]).unwrap();
+        assert!(rewritten.contains("@workgroup_size(8, 8)\nfn first"));
+        assert!(rewritten.contains("@workgroup_size(64, 1, 1)\nfn second"));
+        parse(&rewritten).unwrap();
+
+        let rewritten = with_workgroup_size(source, "first", [4, 16, 1]).unwrap();
+        assert!(rewritten.contains("@workgroup_size(4, 16, 1)\nfn first"));
+
+        assert_eq!(
+            with_workgroup_size(source, "helper_second", [1, 1, 1]),
+            Err(TunerError::MissingAttribute("helper_second".to_string()))
+        );
+        assert!(with_workgroup_size(source, "third", [1, 1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_candidate_sizes_respect_limits() {
+        let limits = wgpu::Limits::default();
+        let one_d = candidate_sizes([1 << 18, 1, 1], [true; 3], [1; 3], &limits);
+        assert_eq!(
+            one_d.iter().map(|s| s[0]).collect::<Vec<_>>(),
+            [16, 32, 64, 128, 256]
+        );
+        assert!(one_d.iter().all(|s| s[1..] == [1, 1]));
+
+        let two_d = candidate_sizes([1024, 1024, 1], [true; 3], [1; 3], &limits);
+        assert!(two_d.contains(&[16, 16, 1]));
+        assert!(two_d.contains(&[64, 4, 1]));
+        assert!(two_d.iter().all(|s| {
+            let total = s[0] * s[1];
+            (16..=256).contains(&total) && s[0] <= 256 && s[1] <= 256
+        }));
+
+        // The z limit of 64 caps that dimension alone
+        let three_d = candidate_sizes([256, 256, 256], [true; 3], [1; 3], &limits);
+        assert!(three_d.contains(&[4, 4, 4]));
+        assert!(three_d.iter().all(|s| s[2] <= 64));
+
+        // Fixed dimensions are kept and count towards the total
+        let fixed = candidate_sizes([4096, 64, 1], [true, false, false], [1, 8, 1], &limits);
+        assert_eq!(fixed.first(), Some(&[2, 8, 1]));
+        assert_eq!(fixed.last(), Some(&[32, 8, 1]));
+
+        // A small grid still gets candidates, none larger than needed
+        let small = candidate_sizes([5, 1, 1], [true; 3], [1; 3], &limits);
+        assert_eq!(small, vec![[8, 1, 1]]);
+
+        // Too many workgroups for a single dispatch
+        let huge = candidate_sizes([u32::MAX, 1, 1], [true; 3], [1; 3], &limits);
+        assert!(huge.is_empty());
+    }
+
+    #[test]
+    fn test_sort_and_best() {
+        let mut report = TuneReport {
+            analysis: analyze(DEFAULT_TUNER_SHADER, "").unwrap(),
+            timing_source: TimingSource::WallClock,
+            invocations: [1024, 1, 1],
+            iterations: 1,
+            results: vec![
+                result([64, 1, 1], 30, 2.0e6),
+                result([16, 1, 1], 50, 1.0e6),
+                result([256, 1, 1], 20, 3.0e6),
+            ],
+            failures: Vec::new(),
+        };
+        assert_eq!(report.best().unwrap().size, [256, 1, 1]);
+        assert_eq!(
+            report.recommendation().as_deref(),
+            Some("@workgroup_size(256)")
+        );
+
+        report.sort(SortColumn::Size, true);
+        let sizes: Vec<u32> = report.results.iter().map(|r| r.size[0]).collect();
+        assert_eq!(sizes, [16, 64, 256]);
+        report.sort(SortColumn::Median, false);
+        let sizes: Vec<u32> = report.results.iter().map(|r| r.size[0]).collect();
+        assert_eq!(sizes, [16, 64, 256]);
+        report.sort(SortColumn::Throughput, false);
+        let sizes: Vec<u32> = report.results.iter().map(|r| r.size[0]).collect();
+        assert_eq!(sizes, [256, 64, 16]);
+        report.sort(SortColumn::Workgroups, true);
+        let sizes: Vec<u32> = report.results.iter().map(|r| r.size[0]).collect();
+        assert_eq!(sizes, [256, 64, 16]);
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_attribute([64, 1, 1]), "@workgroup_size(64)");
+        assert_eq!(format_attribute([8, 8, 1]), "@workgroup_size(8, 8)");
+        assert_eq!(format_attribute([4, 1, 4]), "@workgroup_size(4, 1, 4)");
+        assert_eq!(format_throughput(2.5e9), "2.50 G inv/s");
+        assert_eq!(format_throughput(3.0e6), "3.00 M inv/s");
+        assert_eq!(format_throughput(12.0), "12 inv/s");
+    }
+
+    #[test]
+    fn test_timestamp_durations() {
+        let ticks = [100, 1100, 2000, 2500, 10, 5];
+        let durations = durations_from_timestamps(&ticks, 2.0);
+        assert_eq!(
+            durations,
+            [
+                Duration::from_nanos(2000),
+                Duration::from_nanos(1000),
+                Duration::ZERO
+            ]
+        );
+    }
+}
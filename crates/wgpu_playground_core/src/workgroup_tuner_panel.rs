@@ -0,0 +1,329 @@
+use crate::shader_benchmark::format_ms;
+use crate::workgroup_tuner::{
+    format_attribute, format_throughput, timing_source, tune, SortColumn, TimingSource, TuneReport,
+    TunerConfig, DEFAULT_TUNER_SHADER, MAX_ITERATIONS,
+};
+
+/// Sort state after clicking a column header
+///
+/// Clicking the current column flips the direction; a new column starts
+/// with its most useful end first, fastest or highest throughput.
+pub fn toggle_sort(
+    current: SortColumn,
+    ascending: bool,
+    clicked: SortColumn,
+) -> (SortColumn, bool) {
+    if clicked == current {
+        (current, !ascending)
+    } else {
+        (clicked, clicked != SortColumn::Throughput)
+    }
+}
+
+/// UI panel that benchmarks a compute shader across workgroup sizes and
+/// recommends the fastest one
+pub struct WorkgroupTunerPanel {
+    source: String,
+    config: TunerConfig,
+    report: Option<TuneReport>,
+    error: Option<String>,
+    sort: SortColumn,
+    ascending: bool,
+}
+
+impl Default for WorkgroupTunerPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkgroupTunerPanel {
+    pub fn new() -> Self {
+        Self {
+            source: DEFAULT_TUNER_SHADER.to_string(),
+            config: TunerConfig::default(),
+            report: None,
+            error: None,
+            sort: SortColumn::Throughput,
+            ascending: false,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = source.into();
+    }
+
+    pub fn config(&self) -> &TunerConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: TunerConfig) {
+        self.config = config;
+    }
+
+    pub fn report(&self) -> Option<&TuneReport> {
+        self.report.as_ref()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Tune the current source, keeping the table's sort order
+    pub fn run(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        match tune(device, queue, &self.source, &self.config) {
+            Ok(mut report) => {
+                report.sort(self.sort, self.ascending);
+                self.report = Some(report);
+                self.error = None;
+            }
+            Err(error) => {
+                self.report = None;
+                self.error = Some(error.to_string());
+            }
+        }
+    }
+
+    /// Change the sort column as if its header was clicked
+    pub fn sort_by(&mut self, column: SortColumn) {
+        (self.sort, self.ascending) = toggle_sort(self.sort, self.ascending, column);
+        if let Some(report) = &mut self.report {
+            report.sort(self.sort, self.ascending);
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, device: &wgpu::Device, queue: &wgpu::Queue) {
+        ui.heading("📐 Workgroup Size Tuner");
+        ui.separator();
+        ui.label(
+            "The fastest workgroup size depends on the adapter. Dispatch a compute shader \
+             once per candidate size, compare the timings and copy the recommended \
+             @workgroup_size into your shader. Sizes driven by override constants are \
+             specialised per pipeline; literal sizes are rewritten in the source.",
+        );
+        ui.add_space(10.0);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            self.render_settings(ui, device);
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("▶ Tune").clicked() {
+                    self.run(device, queue);
+                }
+                if ui.button("↺ Reset Shader").clicked() {
+                    self.source = DEFAULT_TUNER_SHADER.to_string();
+                    self.config.entry_point.clear();
+                }
+            });
+            ui.add_space(10.0);
+
+            if let Some(error) = &self.error {
+                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), error);
+                ui.add_space(10.0);
+            }
+            if self.report.is_some() {
+                self.render_report(ui);
+            }
+        });
+    }
+
+    fn render_settings(&mut self, ui: &mut egui::Ui, device: &wgpu::Device) {
+        ui.collapsing("Shader", |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut self.source)
+                    .code_editor()
+                    .desired_rows(14)
+                    .desired_width(f32::INFINITY),
+            );
+        });
+
+        egui::Grid::new("workgroup_tuner_settings")
+            .num_columns(2)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Entry point:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.config.entry_point)
+                        .hint_text("first compute entry point"),
+                );
+                ui.end_row();
+
+                ui.label("Invocations:");
+                ui.horizontal(|ui| {
+                    for (axis, value) in ["x", "y", "z"].iter().zip(&mut self.config.invocations) {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .range(1..=1 << 24)
+                                .prefix(format!("{}: ", axis)),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Total invocations to cover; each size dispatches enough workgroups",
+                );
+                ui.end_row();
+
+                ui.label("Iterations:");
+                ui.add(egui::Slider::new(
+                    &mut self.config.iterations,
+                    1..=MAX_ITERATIONS,
+                ))
+                .on_hover_text("Timed dispatches per size, after one warm-up dispatch");
+                ui.end_row();
+
+                ui.label("Storage buffer size:");
+                let mut kib = self.config.storage_size / 1024;
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut kib)
+                            .range(1..=256 * 1024)
+                            .suffix(" KiB"),
+                    )
+                    .on_hover_text("Size of storage buffers ending in a runtime-sized array")
+                    .changed()
+                {
+                    self.config.storage_size = kib * 1024;
+                }
+                ui.end_row();
+
+                ui.label("Timing:");
+                ui.label(timing_source(device).label());
+                ui.end_row();
+            });
+
+        if timing_source(device) == TimingSource::WallClock {
+            ui.label(
+                egui::RichText::new(
+                    "This device has no TIMESTAMP_QUERY, so each dispatch is timed on the CPU \
+                     and includes submission overhead. Use a large invocation count to keep \
+                     the GPU work dominant.",
+                )
+                .small()
+                .weak(),
+            );
+        }
+    }
+
+    fn render_report(&mut self, ui: &mut egui::Ui) {
+        let Some(report) = &self.report else {
+            return;
+        };
+        let best = report.best().map(|best| best.size);
+
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} via {}, {} sizes, {} iterations each, timed with {}",
+                report.analysis.entry_point,
+                report.analysis.method.label(),
+                report.results.len(),
+                report.iterations,
+                report.timing_source.label(),
+            ));
+        });
+        if let Some(recommendation) = report.recommendation() {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Recommended:").strong());
+                ui.monospace(&recommendation);
+                if ui.small_button("📋 Copy").clicked() {
+                    ui.ctx().copy_text(recommendation.clone());
+                }
+            });
+        }
+        ui.add_space(5.0);
+
+        let mut clicked = None;
+        egui::Grid::new("workgroup_tuner_results")
+            .num_columns(SortColumn::ALL.len())
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for column in SortColumn::ALL {
+                    let mut text = column.label().to_string();
+                    if column == self.sort {
+                        text.push_str(if self.ascending { " ⏶" } else { " ⏷" });
+                    }
+                    if ui
+                        .selectable_label(column == self.sort, egui::RichText::new(text).strong())
+                        .clicked()
+                    {
+                        clicked = Some(column);
+                    }
+                }
+                ui.end_row();
+
+                for result in &report.results {
+                    let size = format_attribute(result.size)
+                        .trim_start_matches("@workgroup_size")
+                        .to_string();
+                    if Some(result.size) == best {
+                        ui.label(egui::RichText::new(format!("★ {}", size)).strong());
+                    } else {
+                        ui.monospace(size);
+                    }
+                    ui.monospace(result.workgroup_invocations().to_string());
+                    let [x, y, z] = result.workgroups;
+                    ui.monospace(format!("{}×{}×{}", x, y, z));
+                    ui.monospace(format_ms(result.timing.median));
+                    ui.monospace(format_ms(result.timing.min));
+                    ui.monospace(format_ms(result.timing.std_dev));
+                    ui.monospace(format_throughput(result.throughput));
+                    ui.end_row();
+                }
+            });
+
+        if !report.failures.is_empty() {
+            ui.add_space(10.0);
+            ui.collapsing(format!("{} sizes failed", report.failures.len()), |ui| {
+                for failure in &report.failures {
+                    ui.label(format!(
+                        "{}: {}",
+                        format_attribute(failure.size),
+                        failure.error
+                    ));
+                }
+            });
+        }
+
+        if let Some(column) = clicked {
+            self.sort_by(column);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_sort() {
+        assert_eq!(
+            toggle_sort(SortColumn::Throughput, false, SortColumn::Throughput),
+            (SortColumn::Throughput, true)
+        );
+        assert_eq!(
+            toggle_sort(SortColumn::Throughput, false, SortColumn::Median),
+            (SortColumn::Median, true)
+        );
+        assert_eq!(
+            toggle_sort(SortColumn::Median, true, SortColumn::Throughput),
+            (SortColumn::Throughput, false)
+        );
+    }
+
+    #[test]
+    fn test_new_panel_uses_default_shader() {
+        let mut panel = WorkgroupTunerPanel::new();
+        assert_eq!(panel.source(), DEFAULT_TUNER_SHADER);
+        assert!(panel.report().is_none());
+        assert!(panel.error().is_none());
+
+        panel.sort_by(SortColumn::Size);
+        assert_eq!((panel.sort, panel.ascending), (SortColumn::Size, true));
+    }
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::asset_loader::{DecodedImage, TextureUpload};
+
+const SIZE: u32 = 64;
+
+fn read_texture(device: &wgpu::Device, queue: &wgpu::Queue, texture: &wgpu::Texture) -> Vec<u8> {
+    // 64 pixels of RGBA are exactly the 256 byte row alignment
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Asset Loader Test Readback"),
+        size: (4 * SIZE * SIZE) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &staging,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * SIZE),
+                rows_per_image: Some(SIZE),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+    let slice = staging.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |result| result.expect("buffer maps"));
+    let _ = device.poll(wgpu::PollType::wait_indefinitely());
+    let data = slice.get_mapped_range().to_vec();
+    staging.unmap();
+    data
+}
+
+#[test]
+fn test_texture_upload_spreads_rows_over_steps() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Asset Loader Test Texture"),
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        // Each row is filled with its own index
+        let rgba = (0..SIZE)
+            .flat_map(|row| std::iter::repeat_n(row as u8, 4 * SIZE as usize))
+            .collect();
+        let image = DecodedImage {
+            width: SIZE,
+            height: SIZE,
+            rgba,
+        };
+        let mut upload = TextureUpload::new(texture, image.clone());
+
+        // 20 rows per step leaves 4 rows for the last one
+        let budget = 20 * 4 * SIZE as u64;
+        let mut steps = 0;
+        while !upload.step(&queue, budget) {
+            steps += 1;
+            assert_eq!(upload.progress(), (20 * steps) as f32 / SIZE as f32);
+        }
+        assert_eq!(steps, 3);
+        assert!(upload.is_done());
+        assert_eq!(upload.progress(), 1.0);
+
+        assert_eq!(read_texture(&device, &queue, upload.texture()), image.rgba);
+    });
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::async_compute::{
+    job_latency, AsyncComputeScheduler, Lane, PollMode, SchedulerConfig,
+};
+use wgpu_playground_core::async_compute_panel::AsyncComputePanel;
+
+fn config(chunks: u32, poll_mode: PollMode) -> SchedulerConfig {
+    SchedulerConfig {
+        job_iterations: 1_000,
+        chunks,
+        chunks_per_tick: 1,
+        frame_iterations: 10,
+        poll_mode,
+    }
+}
+
+#[test]
+fn test_callbacks_complete_in_submission_order() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut scheduler = AsyncComputeScheduler::new(&device);
+        scheduler.set_config(config(4, PollMode::Wait));
+        let job = scheduler.start_job();
+        for _ in 0..4 {
+            scheduler.tick(&device, &queue);
+        }
+        assert_eq!(scheduler.pending_chunks(), 0);
+
+        let submissions = scheduler.submissions();
+        assert_eq!(submissions.len(), 8);
+        assert_eq!(
+            submissions
+                .iter()
+                .filter(|s| s.lane == Lane::Compute)
+                .count(),
+            4
+        );
+        // One queue: callbacks run in the order the work was submitted
+        for submission in &submissions {
+            assert_eq!(submission.completion_order, Some(submission.id));
+            assert!(submission.completed.unwrap() >= submission.submitted);
+        }
+        assert!(job_latency(&submissions, job).is_some());
+    });
+}
+
+#[test]
+fn test_never_poll_completes_on_wait() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut scheduler = AsyncComputeScheduler::new(&device);
+        scheduler.set_config(config(1, PollMode::Never));
+        let job = scheduler.start_job();
+        scheduler.tick(&device, &queue);
+        scheduler.tick(&device, &queue);
+
+        scheduler.wait_idle(&device);
+        let submissions = scheduler.submissions();
+        assert_eq!(submissions.len(), 3);
+        assert!(submissions.iter().all(|s| s.completed.is_some()));
+        assert!(job_latency(&submissions, job).is_some());
+
+        scheduler.clear();
+        assert!(scheduler.submissions().is_empty());
+    });
+}
+
+#[test]
+fn test_panel_creates_scheduler_on_tick() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut panel = AsyncComputePanel::new();
+        panel.set_config(config(0, PollMode::Poll));
+        panel.start_job(&device);
+        panel.tick(&device, &queue);
+
+        let scheduler = panel.scheduler().expect("scheduler created");
+        // Zero chunks is clamped to one
+        assert_eq!(scheduler.config().chunks, 1);
+        assert_eq!(scheduler.pending_chunks(), 0);
+        scheduler.wait_idle(&device);
+        assert_eq!(scheduler.submissions().len(), 2);
+    });
+}
@@ -1,3 +1,4 @@
+use wgpu_playground_core::bind_group::StorageTextureAccess;
 use wgpu_playground_core::bind_group_panel::{
     BindGroupLayoutEntryConfig, BindingTypeConfig, ShaderStagesConfig,
 };
@@ -131,7 +132,9 @@ fn test_binding_type_colors_are_distinct() {
         viz.get_binding_type_color(&BindingTypeConfig::StorageBuffer { read_only: true });
     let texture_color = viz.get_binding_type_color(&BindingTypeConfig::Texture);
     let sampler_color = viz.get_binding_type_color(&BindingTypeConfig::Sampler);
-    let storage_texture_color = viz.get_binding_type_color(&BindingTypeConfig::StorageTexture);
+    let storage_texture_color = viz.get_binding_type_color(&BindingTypeConfig::StorageTexture {
+        access: StorageTextureAccess::WriteOnly,
+    });
 
     // All colors should be distinct
     assert_ne!(uniform_color, storage_color);
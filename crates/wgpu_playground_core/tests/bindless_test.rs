@@ -0,0 +1,96 @@
+mod common;
+
+use common::{create_target, create_test_device, create_test_instance_and_adapter};
+use wgpu_playground_core::bindless::{
+    binding_array_support, grid_radius, request_binding_arrays, FallbackReason, MaterialGrid,
+    MaterialPath,
+};
+use wgpu_playground_core::camera::Camera;
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const WIDTH: u32 = 96;
+const HEIGHT: u32 = 64;
+
+fn render(device: &wgpu::Device, queue: &wgpu::Queue, grid: &mut MaterialGrid) -> wgpu::Texture {
+    let target = create_target(device, WIDTH, HEIGHT, wgpu::TextureFormat::Rgba8Unorm);
+    let view = target.create_view(&Default::default());
+    let camera = Camera::framing([0.0; 3], grid_radius());
+    grid.update(
+        queue,
+        camera.view_projection(WIDTH as f32 / HEIGHT as f32),
+        0.0,
+    );
+    grid.render(device, queue, &view, (WIDTH, HEIGHT), wgpu::Color::BLACK);
+    target
+}
+
+#[test]
+fn test_atlas_path_draws_every_material() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut grid = MaterialGrid::new(&device, &queue, wgpu::TextureFormat::Rgba8Unorm, false);
+        assert_eq!(grid.path(), MaterialPath::Atlas);
+        let expected = binding_array_support(device.features(), &device.limits())
+            .err()
+            .unwrap_or(FallbackReason::Chosen);
+        assert_eq!(grid.fallback(), Some(&expected));
+        assert_eq!(grid.textures_bound(), 1);
+
+        let target = render(&device, &queue, &mut grid);
+        let image = capture_texture(&device, &queue, &target)
+            .await
+            .expect("image captures");
+        let mut colors: Vec<_> = image.pixels().map(|p| p.0).collect();
+        colors.sort_unstable();
+        colors.dedup();
+        assert!(colors.len() >= 16, "{} colors", colors.len());
+    });
+}
+
+#[test]
+fn test_binding_array_path_matches_atlas() {
+    pollster::block_on(async {
+        let Some((_instance, adapter)) = create_test_instance_and_adapter().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        if let Err(reason) = binding_array_support(adapter.features(), &adapter.limits()) {
+            eprintln!("Skipping test: {}", reason);
+            return;
+        }
+        let mut descriptor = wgpu::DeviceDescriptor::default();
+        request_binding_arrays(&adapter, &mut descriptor);
+        let (device, queue) = adapter
+            .request_device(&descriptor)
+            .await
+            .expect("device with binding arrays");
+
+        let mut bindless =
+            MaterialGrid::new(&device, &queue, wgpu::TextureFormat::Rgba8Unorm, true);
+        assert_eq!(bindless.path(), MaterialPath::BindingArray);
+        assert_eq!(bindless.fallback(), None);
+        let mut atlas = MaterialGrid::new(&device, &queue, wgpu::TextureFormat::Rgba8Unorm, false);
+        assert_eq!(atlas.fallback(), Some(&FallbackReason::Chosen));
+
+        let bindless_target = render(&device, &queue, &mut bindless);
+        let atlas_target = render(&device, &queue, &mut atlas);
+        let bindless_image = capture_texture(&device, &queue, &bindless_target)
+            .await
+            .expect("image captures");
+        let atlas_image = capture_texture(&device, &queue, &atlas_target)
+            .await
+            .expect("image captures");
+        // Both sample the same texels; only filtering at cell edges may differ
+        let total: u64 = bindless_image
+            .pixels()
+            .zip(atlas_image.pixels())
+            .flat_map(|(a, b)| (0..3).map(move |c| a.0[c].abs_diff(b.0[c]) as u64))
+            .sum();
+        let mean = total as f64 / (WIDTH * HEIGHT * 3) as f64;
+        assert!(mean < 2.0, "mean difference {}", mean);
+    });
+}
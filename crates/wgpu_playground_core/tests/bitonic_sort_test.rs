@@ -0,0 +1,100 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::bitonic_sort::{
+    cpu_sort, random_keys, BitonicSorter, KeyType, SortParams, SortStrategy, MIN_KEYS,
+    VISUALIZED_KEYS,
+};
+
+#[test]
+fn test_every_strategy_matches_the_cpu() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let sorter = BitonicSorter::new(&device);
+        for &key_type in KeyType::all() {
+            // Not a power of two, so the keys are padded
+            let keys = random_keys(70_001, key_type, 42);
+            let expected = cpu_sort(&keys, key_type);
+            for &strategy in SortStrategy::all() {
+                let run = sorter
+                    .sort(&device, &queue, &keys, key_type, strategy)
+                    .expect("sort runs");
+                assert!(
+                    run.output == expected,
+                    "{:?} {:?} differs from the CPU",
+                    key_type,
+                    strategy
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn test_comparison_is_verified() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let sorter = BitonicSorter::new(&device);
+        let params = SortParams {
+            key_count: 1 << 16,
+            key_type: KeyType::F32,
+        };
+        let comparison = sorter
+            .compare(&device, &queue, params)
+            .expect("comparison runs");
+        assert!(comparison.verified());
+        assert_eq!(comparison.strategies.len(), SortStrategy::all().len());
+        assert!(comparison.speedup().is_some());
+    });
+}
+
+#[test]
+fn test_snapshots_end_sorted() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let sorter = BitonicSorter::new(&device);
+        let snapshots = sorter
+            .snapshots(&device, &queue, KeyType::U32)
+            .expect("snapshots run");
+        // The keys before the first step and after each of 9 * 10 / 2 steps
+        assert_eq!(snapshots.len(), 46);
+        assert!(snapshots
+            .iter()
+            .all(|row| row.len() == VISUALIZED_KEYS as usize));
+        assert_eq!(snapshots[45], cpu_sort(&snapshots[0], KeyType::U32));
+    });
+}
+
+#[test]
+fn test_too_few_keys_are_rejected() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let sorter = BitonicSorter::new(&device);
+        let keys = vec![1; MIN_KEYS as usize - 1];
+        assert!(sorter
+            .sort(
+                &device,
+                &queue,
+                &keys,
+                KeyType::U32,
+                SortStrategy::Workgroup
+            )
+            .is_err());
+    });
+}
@@ -0,0 +1,248 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu::util::DeviceExt;
+use wgpu_playground_core::blend_visualizer::{truth_table, DESTINATION_SWATCHES, SOURCE_SWATCHES};
+use wgpu_playground_core::render_pipeline::{
+    BlendComponent, BlendFactor, BlendOperation, BlendState,
+};
+
+const SHADER: &str = r#"
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+@vertex
+fn vs_main(@location(0) position: vec2<f32>, @location(1) color: vec4<f32>) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(position, 0.0, 1.0);
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
+"#;
+
+const CONSTANT: [f32; 4] = [0.25, 0.5, 0.75, 0.5];
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    module: &wgpu::ShaderModule,
+    blend: wgpu::BlendState,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Blend Test Pipeline"),
+        layout: None,
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some("vs_main"),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: 6 * 4,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4],
+            }],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// Blend every source swatch over every destination swatch on the GPU,
+/// one texel per pair, and read the texels back as floats
+fn render_table(device: &wgpu::Device, queue: &wgpu::Queue, state: &BlendState) -> Vec<[f32; 4]> {
+    let (columns, rows) = (
+        SOURCE_SWATCHES.len() as u32,
+        DESTINATION_SWATCHES.len() as u32,
+    );
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Blend Test Shader"),
+        source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+    });
+    let replace = create_pipeline(device, &module, wgpu::BlendState::REPLACE);
+    let blend = create_pipeline(device, &module, state.to_wgpu());
+
+    // A triangle covering the viewport per color: destinations first, then sources
+    let mut vertices: Vec<f32> = Vec::new();
+    let colors = DESTINATION_SWATCHES.iter().chain(SOURCE_SWATCHES);
+    for (_, color) in colors {
+        for position in [[-1.0, -1.0], [3.0, -1.0], [-1.0, 3.0]] {
+            vertices.extend_from_slice(&position);
+            vertices.extend_from_slice(color);
+        }
+    }
+    let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Blend Test Vertices"),
+        contents: bytemuck::cast_slice(&vertices),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+
+    let texture = create_target(device, columns, rows, wgpu::TextureFormat::Rgba8Unorm);
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Blend Test Encoder"),
+    });
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Blend Test Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.set_blend_constant(wgpu::Color {
+            r: CONSTANT[0] as f64,
+            g: CONSTANT[1] as f64,
+            b: CONSTANT[2] as f64,
+            a: CONSTANT[3] as f64,
+        });
+        for row in 0..rows {
+            for column in 0..columns {
+                pass.set_viewport(column as f32, row as f32, 1.0, 1.0, 0.0, 1.0);
+                pass.set_pipeline(&replace);
+                pass.draw(row * 3..row * 3 + 3, 0..1);
+                let src = (rows + column) * 3;
+                pass.set_pipeline(&blend);
+                pass.draw(src..src + 3, 0..1);
+            }
+        }
+    }
+
+    let padded_bytes_per_row = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Blend Test Readback"),
+        size: (padded_bytes_per_row * rows) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &staging,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(rows),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let slice = staging.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    device
+        .poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        })
+        .unwrap();
+    let data = slice.get_mapped_range();
+    data.chunks(padded_bytes_per_row as usize)
+        .flat_map(|row| {
+            row[..(columns * 4) as usize]
+                .chunks(4)
+                .map(|texel| std::array::from_fn(|i| texel[i] as f32 / 255.0))
+                .collect::<Vec<[f32; 4]>>()
+        })
+        .collect()
+}
+
+fn assert_matches_gpu(device: &wgpu::Device, queue: &wgpu::Queue, state: BlendState) {
+    let gpu = render_table(device, queue, &state);
+    let cpu = truth_table(&state, CONSTANT);
+    for (row, (dst_name, _)) in DESTINATION_SWATCHES.iter().enumerate() {
+        for (column, (src_name, _)) in SOURCE_SWATCHES.iter().enumerate() {
+            let expected = cpu[row][column];
+            let actual = gpu[row * SOURCE_SWATCHES.len() + column];
+            for (e, a) in expected.iter().zip(actual) {
+                // Both colors are quantized to 8 bits before blending
+                assert!(
+                    (e - a).abs() <= 2.5 / 255.0,
+                    "{:?}: {} over {}: expected {:?}, got {:?}",
+                    state,
+                    src_name,
+                    dst_name,
+                    expected,
+                    actual
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_truth_table_matches_gpu_blending() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let component = BlendComponent::new;
+        let straight_over = component(
+            BlendFactor::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha,
+            BlendOperation::Add,
+        );
+        let states = [
+            BlendState::replace(),
+            BlendState::alpha_blending(),
+            BlendState::new(straight_over, straight_over),
+            BlendState::new(
+                component(BlendFactor::One, BlendFactor::One, BlendOperation::Add),
+                component(BlendFactor::One, BlendFactor::One, BlendOperation::Max),
+            ),
+            BlendState::new(
+                component(
+                    BlendFactor::Dst,
+                    BlendFactor::OneMinusSrc,
+                    BlendOperation::ReverseSubtract,
+                ),
+                component(BlendFactor::One, BlendFactor::One, BlendOperation::Min),
+            ),
+            BlendState::new(
+                component(
+                    BlendFactor::SrcAlphaSaturated,
+                    BlendFactor::Constant,
+                    BlendOperation::Subtract,
+                ),
+                component(
+                    BlendFactor::OneMinusDstAlpha,
+                    BlendFactor::OneMinusConstant,
+                    BlendOperation::Add,
+                ),
+            ),
+        ];
+        for state in states {
+            assert_matches_gpu(&device, &queue, state);
+        }
+    });
+}
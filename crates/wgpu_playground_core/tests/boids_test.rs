@@ -0,0 +1,74 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::boids::{Boid, Flock, DEFAULT_BOIDS};
+use wgpu_playground_core::buffer_io::read_buffer;
+
+const ASPECT: f32 = 1.5;
+
+fn read_boids(device: &wgpu::Device, queue: &wgpu::Queue, flock: &Flock) -> Vec<Boid> {
+    let bytes = read_buffer(device, queue, flock.boids_buffer()).expect("boids read back");
+    bytemuck::cast_slice::<u8, Boid>(&bytes)[..flock.boid_count() as usize].to_vec()
+}
+
+fn step(device: &wgpu::Device, queue: &wgpu::Queue, flock: &mut Flock, target: &wgpu::TextureView) {
+    flock.update(queue, ASPECT, 0.016);
+    flock.render(device, queue, target, wgpu::Color::BLACK);
+}
+
+#[test]
+fn test_boids_move_and_stay_in_the_world() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, 96, 64, wgpu::TextureFormat::Rgba8UnormSrgb)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut flock = Flock::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb, ASPECT);
+        flock.set_boid_count(512);
+        flock.add_obstacle([0.0, 0.0]);
+        let before = read_boids(&device, &queue, &flock);
+        for _ in 0..20 {
+            step(&device, &queue, &mut flock, &target);
+        }
+        let after = read_boids(&device, &queue, &flock);
+
+        assert_ne!(before, after);
+        for boid in &after {
+            let [x, y] = boid.position;
+            assert!(
+                x.abs() <= ASPECT && y.abs() <= 1.0,
+                "{:?} left the world",
+                boid
+            );
+            let speed = boid.velocity[0].hypot(boid.velocity[1]);
+            assert!(
+                speed <= flock.params.max_speed * 1.001,
+                "{:?} too fast",
+                boid
+            );
+        }
+    });
+}
+
+#[test]
+fn test_paused_flock_does_not_move() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, 96, 64, wgpu::TextureFormat::Rgba8UnormSrgb)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut flock = Flock::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb, ASPECT);
+        assert_eq!(flock.boid_count(), DEFAULT_BOIDS);
+        step(&device, &queue, &mut flock, &target);
+        flock.paused = true;
+        let before = read_boids(&device, &queue, &flock);
+        step(&device, &queue, &mut flock, &target);
+        assert_eq!(before, read_boids(&device, &queue, &flock));
+    });
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::buffer_image::{BufferImage, BufferImageSettings, Colormap, ElementType};
+use wgpu_playground_core::buffer_inspector::{BufferInspector, ViewMode};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+fn f32_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+#[test]
+fn test_buffer_image_grayscale_pixels() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut image = BufferImage::new(&device);
+        let settings = BufferImageSettings {
+            width: 2,
+            element_type: ElementType::Float32,
+            colormap: Colormap::Grayscale,
+            range: None,
+        };
+        // Three values in rows of two: the last cell is past the end
+        let layout = image
+            .render(&device, &queue, &f32_bytes(&[0.0, 1.0, 0.5]), &settings)
+            .expect("image renders");
+        assert_eq!((layout.width, layout.height), (2, 2));
+        assert_eq!(layout.elements, 3);
+        assert_eq!(image.range(), Some((0.0, 1.0)));
+
+        let pixels = capture_texture(&device, &queue, image.texture().unwrap())
+            .await
+            .expect("texture captures");
+        assert!(pixels.get_pixel(0, 0)[0] < 8, "minimum maps to black");
+        assert!(pixels.get_pixel(1, 0)[0] > 247, "maximum maps to white");
+        let middle = pixels.get_pixel(0, 1)[0];
+        assert!((100..156).contains(&middle), "middle is gray: {}", middle);
+    });
+}
+
+#[test]
+fn test_buffer_image_u8_with_fixed_range() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut image = BufferImage::new(&device);
+        let settings = BufferImageSettings {
+            width: 4,
+            element_type: ElementType::Uint8,
+            colormap: Colormap::Grayscale,
+            range: Some((0.0, 100.0)),
+        };
+        let layout = image
+            .render(&device, &queue, &[0, 50, 100, 255, 7], &settings)
+            .expect("image renders");
+        assert_eq!((layout.width, layout.height), (4, 2));
+
+        let pixels = capture_texture(&device, &queue, image.texture().unwrap())
+            .await
+            .expect("texture captures");
+        assert!(pixels.get_pixel(0, 0)[0] < 8);
+        // Values above the range are clamped to its end
+        assert_eq!(pixels.get_pixel(2, 0), pixels.get_pixel(3, 0));
+        assert!(pixels.get_pixel(2, 0)[0] > 247);
+    });
+}
+
+#[test]
+fn test_buffer_image_rejects_too_wide_grid() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut image = BufferImage::new(&device);
+        let settings = BufferImageSettings {
+            width: device.limits().max_texture_dimension_2d + 1,
+            ..BufferImageSettings::default()
+        };
+        assert!(image
+            .render(&device, &queue, &f32_bytes(&[1.0; 4]), &settings)
+            .is_err());
+    });
+}
+
+#[test]
+fn test_buffer_inspector_image_view() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut inspector = BufferInspector::new();
+        inspector.set_view_mode(ViewMode::Image);
+        assert!(inspector.update_image(&device, &queue).is_none());
+
+        // A 16x16 heightfield
+        let heights: Vec<f32> = (0..256).map(|i| (i % 16) as f32).collect();
+        inspector.load_data(f32_bytes(&heights));
+        inspector.set_image_settings(BufferImageSettings {
+            width: 16,
+            ..BufferImageSettings::default()
+        });
+
+        let layout = inspector
+            .update_image(&device, &queue)
+            .cloned()
+            .expect("data is loaded")
+            .expect("image renders");
+        assert_eq!((layout.width, layout.height), (16, 16));
+        assert_eq!(layout.truncated, 0);
+    });
+}
@@ -0,0 +1,397 @@
+//! Integration tests for exporting playground state as standalone projects
+
+use base64::prelude::*;
+use std::path::Path;
+use std::process::Command;
+use wgpu_playground_core::code_generator::{
+    CodeGenConfig, CodeGenerator, ExampleType, ExportTarget,
+};
+use wgpu_playground_core::state::{
+    BindGroupEntryState, BindGroupPanelState, BufferPanelState, ComputePipelinePanelState,
+    PlaygroundState, RenderPipelinePanelState, SamplerPanelState, ShaderEditorState,
+    TexturePanelState,
+};
+
+const SHADER: &str = "struct Params {
+    tint: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var color_texture: texture_2d<f32>;
+@group(0) @binding(2) var color_sampler: sampler;
+@group(0) @binding(3) var<storage, read_write> frames: array<u32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    return textureSample(color_texture, color_sampler, position.xy / 800.0) * params.tint;
+}
+
+@compute @workgroup_size(1)
+fn cs_main() {
+    frames[0] = frames[0] + 1u;
+}
+";
+
+/// A 1x1 white PNG
+const PNG: [u8; 69] = [
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x90, 0x77, 0x53,
+    0xDE, 0x00, 0x00, 0x00, 0x0C, 0x49, 0x44, 0x41, 0x54, 0x08, 0xD7, 0x63, 0xF8, 0xFF, 0xFF, 0x3F,
+    0x00, 0x05, 0xFE, 0x02, 0xFE, 0xDC, 0xCC, 0x59, 0xE7, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E,
+    0x44, 0xAE, 0x42, 0x60, 0x82,
+];
+
+fn entry(binding: u32, binding_type: &str, fragment: bool, compute: bool) -> BindGroupEntryState {
+    BindGroupEntryState {
+        binding,
+        visibility_vertex: false,
+        visibility_fragment: fragment,
+        visibility_compute: compute,
+        binding_type: binding_type.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Every panel configured, with a depth-tested multisampled render pipeline
+fn full_state() -> PlaygroundState {
+    PlaygroundState {
+        shader_editor: Some(ShaderEditorState {
+            source_code: SHADER.to_string(),
+            label: "shader".to_string(),
+            ..Default::default()
+        }),
+        // Not a storage buffer, so binding 3 gets a placeholder
+        buffer_panel: Some(BufferPanelState {
+            label: "params".to_string(),
+            size: "16".to_string(),
+            usage_uniform: true,
+            usage_copy_dst: true,
+            ..Default::default()
+        }),
+        texture_panel: Some(TexturePanelState {
+            label: "photo".to_string(),
+            width: "256".to_string(),
+            height: "256".to_string(),
+            depth: "1".to_string(),
+            mip_levels: "1".to_string(),
+            sample_count: "1".to_string(),
+            format: "Rgba8UnormSrgb".to_string(),
+            dimension: "D2".to_string(),
+            usage_texture_binding: true,
+            image_data: Some(BASE64_STANDARD.encode(PNG)),
+            ..Default::default()
+        }),
+        sampler_panel: Some(SamplerPanelState {
+            label: "nearest".to_string(),
+            address_mode_u: "ClampToEdge".to_string(),
+            address_mode_v: "MirrorRepeat".to_string(),
+            address_mode_w: "Repeat".to_string(),
+            mag_filter: "Nearest".to_string(),
+            min_filter: "Nearest".to_string(),
+            mipmap_filter: "Nearest".to_string(),
+            lod_min_clamp: "0.0".to_string(),
+            lod_max_clamp: "8.0".to_string(),
+            compare: None,
+            max_anisotropy: "1".to_string(),
+        }),
+        render_pipeline_panel: Some(RenderPipelinePanelState {
+            label: "tinted".to_string(),
+            vertex_entry_point: "vs_main".to_string(),
+            fragment_entry_point: "fs_main".to_string(),
+            topology: "TriangleList".to_string(),
+            cull_mode: "Back".to_string(),
+            front_face: "Ccw".to_string(),
+            polygon_mode: "Fill".to_string(),
+            enable_depth_stencil: true,
+            depth_format: "Depth24Plus".to_string(),
+            depth_write_enabled: true,
+            depth_compare: "Less".to_string(),
+            stencil_read_mask: "0xFFFFFFFF".to_string(),
+            stencil_write_mask: "0xFFFFFFFF".to_string(),
+            sample_count: 4,
+            blend_enabled: true,
+            color_blend_src: "SrcAlpha".to_string(),
+            color_blend_dst: "OneMinusSrcAlpha".to_string(),
+            color_blend_op: "Add".to_string(),
+            alpha_blend_src: "One".to_string(),
+            alpha_blend_dst: "Zero".to_string(),
+            alpha_blend_op: "Add".to_string(),
+            write_red: true,
+            write_green: true,
+            write_blue: true,
+            write_alpha: true,
+            ..Default::default()
+        }),
+        compute_pipeline_panel: Some(ComputePipelinePanelState {
+            label: "frame_counter".to_string(),
+            entry_point: "cs_main".to_string(),
+        }),
+        bind_group_panel: Some(BindGroupPanelState {
+            label: "material".to_string(),
+            entries: vec![
+                entry(0, "UniformBuffer", true, false),
+                entry(1, "Texture", true, false),
+                entry(2, "Sampler", true, false),
+                entry(3, "StorageBuffer", false, true),
+            ],
+        }),
+        ..Default::default()
+    }
+}
+
+fn export(name: &str, config: CodeGenConfig) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    CodeGenerator::new(config).generate(&dir).unwrap();
+    dir
+}
+
+/// Run `cargo check` on an exported project, sharing one target directory
+/// between the exports
+fn cargo_check(project: &Path) {
+    let output = Command::new(env!("CARGO"))
+        .arg("check")
+        .arg("--quiet")
+        .current_dir(project)
+        .env(
+            "CARGO_TARGET_DIR",
+            std::env::temp_dir().join("wgpu_playground_export_target"),
+        )
+        .output()
+        .expect("Failed to run cargo");
+    assert!(
+        output.status.success(),
+        "{} doesn't compile:\n{}",
+        project.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_full_state_export_emits_every_resource() {
+    let dir = export(
+        "wgpu_test_full_state_export",
+        CodeGenConfig::new("full_state_export".to_string())
+            .with_playground_state(full_state())
+            .with_workgroups(2, 1, 1),
+    );
+    let main_rs = std::fs::read_to_string(dir.join("src").join("main.rs")).unwrap();
+
+    // Texture with its format, usages and embedded image
+    assert!(main_rs.contains("format: wgpu::TextureFormat::Rgba8UnormSrgb"));
+    assert!(main_rs
+        .contains("usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING"));
+    assert!(main_rs.contains("include_bytes!(\"texture.rgba\")"));
+    assert_eq!(
+        std::fs::read(dir.join("src").join("texture.rgba")).unwrap(),
+        vec![255; 4]
+    );
+
+    // Sampler settings
+    assert!(main_rs.contains("address_mode_v: wgpu::AddressMode::MirrorRepeat"));
+    assert!(main_rs.contains("mag_filter: wgpu::FilterMode::Nearest"));
+    assert!(main_rs.contains("lod_max_clamp: 8.0"));
+
+    // Bind group bound to the configured resources, with a placeholder for
+    // the storage buffer
+    assert!(main_rs.contains("create_bind_group_layout"));
+    assert!(main_rs.contains("ty: wgpu::BufferBindingType::Uniform"));
+    assert!(main_rs.contains("resource: buffer.as_entire_binding()"));
+    assert!(main_rs.contains("wgpu::BindingResource::TextureView(&texture_view)"));
+    assert!(main_rs.contains("wgpu::BindingResource::Sampler(&sampler)"));
+    assert!(main_rs.contains("binding_3_buffer.as_entire_binding()"));
+    assert!(main_rs.contains("bind_group_layouts: &[Some(&bind_group_layout)]"));
+
+    // Both pipelines run every frame
+    assert!(main_rs.contains("cull_mode: Some(wgpu::Face::Back)"));
+    assert!(main_rs.contains("src_factor: wgpu::BlendFactor::SrcAlpha"));
+    assert!(main_rs.contains("count: 4"));
+    assert!(main_rs.contains("view: &self.depth_view"));
+    assert!(main_rs.contains("resolve_target: Some(&view)"));
+    assert!(main_rs.contains("compute_pass.dispatch_workgroups(2, 1, 1)"));
+    assert!(main_rs.contains("render_pass.set_bind_group(0, &self.bind_group, &[])"));
+    assert!(main_rs.contains("fn create_attachment("));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_export_skips_image_of_non_rgba8_texture() {
+    let mut state = full_state();
+    if let Some(texture) = &mut state.texture_panel {
+        texture.format = "Rgba16Float".to_string();
+    }
+    let dir = export(
+        "wgpu_test_non_rgba8_export",
+        CodeGenConfig::new("non_rgba8_export".to_string()).with_playground_state(state),
+    );
+    let main_rs = std::fs::read_to_string(dir.join("src").join("main.rs")).unwrap();
+
+    assert!(!main_rs.contains("include_bytes!"));
+    assert!(!dir.join("src").join("texture.rgba").exists());
+    assert!(main_rs.contains("width: 256"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cpp_export_emits_every_resource() {
+    let dir = export(
+        "wgpu_test_cpp_export",
+        CodeGenConfig::new("cpp_export".to_string())
+            .with_playground_state(full_state())
+            .with_workgroups(2, 1, 1)
+            .with_target(ExportTarget::Cpp),
+    );
+    let cmake = std::fs::read_to_string(dir.join("CMakeLists.txt")).unwrap();
+    let main_cpp = std::fs::read_to_string(dir.join("main.cpp")).unwrap();
+    let texture_data = std::fs::read_to_string(dir.join("texture_data.h")).unwrap();
+
+    assert!(!dir.join("Cargo.toml").exists());
+    assert!(cmake.contains("add_executable(cpp_export main.cpp)"));
+    assert!(cmake.contains("dawn::webgpu_dawn"));
+
+    // Texture with its embedded image, and the sampler settings
+    assert!(main_cpp.contains("#include \"texture_data.h\""));
+    assert!(main_cpp.contains("texture_descriptor.format = WGPUTextureFormat_RGBA8UnormSrgb"));
+    assert!(main_cpp.contains("wgpuQueueWriteTexture(state.queue"));
+    assert!(texture_data.contains("0xff, 0xff, 0xff, 0xff,"));
+    assert!(main_cpp.contains("addressModeV = WGPUAddressMode_MirrorRepeat"));
+    assert!(main_cpp.contains("lodMaxClamp = 8.0f"));
+
+    // Bind group with a placeholder for the storage buffer
+    assert!(main_cpp.contains("layout_entries[0].buffer.type = WGPUBufferBindingType_Uniform"));
+    assert!(main_cpp.contains("group_entries[0].buffer = state.buffer"));
+    assert!(main_cpp.contains("group_entries[1].textureView = state.texture_view"));
+    assert!(main_cpp.contains("group_entries[3].buffer = binding_3_buffer"));
+    assert!(main_cpp.contains("wgpuBufferRelease(binding_3_buffer)"));
+
+    // Both pipelines run every frame
+    assert!(main_cpp.contains("primitive.cullMode = WGPUCullMode_Back"));
+    assert!(main_cpp.contains("blend.color.srcFactor = WGPUBlendFactor_SrcAlpha"));
+    assert!(main_cpp.contains("multisample.count = 4"));
+    assert!(main_cpp.contains("color_attachment.resolveTarget = view"));
+    assert!(main_cpp.contains("wgpuComputePassEncoderDispatchWorkgroups(compute_pass, 2, 1, 1)"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_python_export_emits_every_resource() {
+    let dir = export(
+        "wgpu_test_python_export",
+        CodeGenConfig::new("python_export".to_string())
+            .with_playground_state(full_state())
+            .with_workgroups(2, 1, 1)
+            .with_target(ExportTarget::Python),
+    );
+    let main_py = std::fs::read_to_string(dir.join("main.py")).unwrap();
+    let requirements = std::fs::read_to_string(dir.join("requirements.txt")).unwrap();
+
+    assert!(!dir.join("Cargo.toml").exists());
+    assert!(requirements.contains("wgpu>="));
+    assert!(requirements.contains("rendercanvas>="));
+
+    // Texture with its image, and the sampler settings
+    assert_eq!(
+        std::fs::read(dir.join("texture.rgba")).unwrap(),
+        [0xff, 0xff, 0xff, 0xff]
+    );
+    assert!(main_py.contains("format=wgpu.TextureFormat.rgba8unorm_srgb"));
+    assert!(main_py.contains("device.queue.write_texture("));
+    assert!(main_py.contains("address_mode_v=wgpu.AddressMode.mirror_repeat"));
+    assert!(main_py.contains("lod_max_clamp=8.0"));
+
+    // Bind group with a placeholder for the storage buffer
+    assert!(main_py.contains("\"buffer\": {\"type\": wgpu.BufferBindingType.uniform}"));
+    assert!(main_py.contains("{\"binding\": 1, \"resource\": texture_view}"));
+    assert!(main_py.contains("\"buffer\": binding_3_buffer"));
+
+    // Both pipelines run every frame
+    assert!(main_py.contains("\"cull_mode\": wgpu.CullMode.back"));
+    assert!(main_py.contains("\"src_factor\": wgpu.BlendFactor.src_alpha"));
+    assert!(main_py.contains("\"count\": 4"));
+    assert!(main_py.contains("\"resolve_target\": view"));
+    assert!(main_py.contains("compute_pass.dispatch_workgroups(2, 1, 1)"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[ignore = "runs python3 on the exported scripts"]
+fn test_exported_python_scripts_compile() {
+    let projects = [
+        export(
+            "wgpu_test_python_compile_full_state",
+            CodeGenConfig::new("python_compile_full_state".to_string())
+                .with_playground_state(full_state())
+                .with_target(ExportTarget::Python),
+        ),
+        export(
+            "wgpu_test_python_compile_compute",
+            CodeGenConfig::new("python_compile_compute".to_string())
+                .with_example_type(ExampleType::Compute)
+                .with_target(ExportTarget::Python),
+        ),
+    ];
+
+    for project in &projects {
+        let output = Command::new("python3")
+            .args(["-m", "py_compile", "main.py"])
+            .current_dir(project)
+            .output()
+            .expect("Failed to run python3");
+        assert!(
+            output.status.success(),
+            "{} doesn't compile:\n{}",
+            project.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        std::fs::remove_dir_all(project).unwrap();
+    }
+}
+
+#[test]
+#[ignore = "runs cargo check on the exported projects, which fetches their dependencies"]
+fn test_exported_projects_compile() {
+    let state = full_state();
+    let projects = [
+        export(
+            "wgpu_test_compile_full_state",
+            CodeGenConfig::new("compile_full_state".to_string())
+                .with_playground_state(state.clone()),
+        ),
+        export(
+            "wgpu_test_compile_render_only",
+            CodeGenConfig::new("compile_render_only".to_string()).with_playground_state(
+                PlaygroundState {
+                    compute_pipeline_panel: None,
+                    bind_group_panel: None,
+                    ..state
+                },
+            ),
+        ),
+        export(
+            "wgpu_test_compile_triangle",
+            CodeGenConfig::new("compile_triangle".to_string()),
+        ),
+        export(
+            "wgpu_test_compile_compute",
+            CodeGenConfig::new("compile_compute".to_string())
+                .with_example_type(ExampleType::Compute),
+        ),
+    ];
+
+    for project in &projects {
+        cargo_check(project);
+    }
+    for project in &projects {
+        std::fs::remove_dir_all(project).unwrap();
+    }
+}
@@ -0,0 +1,129 @@
+use wgpu_playground_core::command_palette::{
+    Command, CommandCategory, CommandKind, CommandPalette, CommandRegistry,
+};
+use wgpu_playground_core::keybindings::Action;
+use wgpu_playground_core::keybindings_panel::KeyBindingsPanel;
+use wgpu_playground_core::preset_panel::PresetPanel;
+
+fn registry() -> CommandRegistry {
+    let mut registry = CommandRegistry::new();
+    registry.register(Command::new(
+        CommandKind::Action(Action::CompileShader),
+        "Compile shader",
+    ));
+    registry.register(Command::new(
+        CommandKind::Action(Action::CaptureFrame),
+        "Capture next frame in RenderDoc",
+    ));
+    registry.register(
+        Command::new(CommandKind::OpenPanel("nav-textures"), "Open Textures")
+            .with_keywords(["Create and configure GPU textures"]),
+    );
+    registry.register(
+        Command::new(
+            CommandKind::ApplyPreset("pbr_material"),
+            "Apply preset: PBR Material",
+        )
+        .with_keywords(["metallic", "roughness"]),
+    );
+    registry.register(Command::new(
+        CommandKind::LoadExample("triangle"),
+        "Load example: Triangle",
+    ));
+    registry
+}
+
+fn titles(commands: Vec<&Command>) -> Vec<&str> {
+    commands
+        .into_iter()
+        .map(|command| command.title.as_str())
+        .collect()
+}
+
+#[test]
+fn test_search_ranks_best_match_first() {
+    let registry = registry();
+    assert_eq!(titles(registry.search("compile")), vec!["Compile shader"]);
+    assert_eq!(
+        registry.search("tri")[0].kind,
+        CommandKind::LoadExample("triangle")
+    );
+    assert!(registry.search("zzz").is_empty());
+    assert_eq!(registry.search("").len(), registry.len());
+}
+
+#[test]
+fn test_every_word_must_match() {
+    let registry = registry();
+    assert_eq!(
+        titles(registry.search("preset pbr")),
+        vec!["Apply preset: PBR Material"]
+    );
+    assert!(registry.search("preset triangle").is_empty());
+}
+
+#[test]
+fn test_keywords_find_commands() {
+    let registry = registry();
+    assert_eq!(
+        registry.search("roughness")[0].kind,
+        CommandKind::ApplyPreset("pbr_material")
+    );
+    // Title matches outrank keyword matches
+    let mut registry = registry;
+    registry.register(Command::new(
+        CommandKind::OpenPanel("nav-models"),
+        "Open Roughness Maps",
+    ));
+    assert_eq!(
+        registry.search("roughness")[0].kind,
+        CommandKind::OpenPanel("nav-models")
+    );
+}
+
+#[test]
+fn test_palette_runs_selection_and_remembers_it() {
+    let mut palette = CommandPalette::new();
+    assert!(!palette.is_open());
+    palette.open(registry());
+    assert!(palette.is_open());
+
+    palette.set_query("capture");
+    assert_eq!(
+        palette.confirm(),
+        Some(CommandKind::Action(Action::CaptureFrame))
+    );
+    assert!(!palette.is_open());
+
+    // Recently run commands come first without a query
+    palette.open(registry());
+    assert_eq!(
+        palette.matches()[0].kind,
+        CommandKind::Action(Action::CaptureFrame)
+    );
+    assert_eq!(
+        palette.recent(),
+        &[CommandKind::Action(Action::CaptureFrame)]
+    );
+
+    palette.set_query("no such command");
+    assert_eq!(palette.confirm(), None);
+    assert!(palette.is_open());
+}
+
+#[test]
+fn test_panels_register_commands() {
+    let mut registry = CommandRegistry::new();
+    KeyBindingsPanel::new().register_commands(&mut registry);
+    PresetPanel::new().register_commands(&mut registry);
+    let categories: Vec<CommandCategory> = registry
+        .commands()
+        .iter()
+        .map(|command| command.kind.category())
+        .collect();
+    assert!(categories.contains(&CommandCategory::Action));
+    assert!(categories.contains(&CommandCategory::Preset));
+    assert!(registry
+        .get(CommandKind::Action(Action::CaptureFrame))
+        .is_some());
+}
@@ -208,6 +208,33 @@ pub async fn create_test_instance_and_adapter() -> Option<(Instance, Adapter)> {
     Some((instance, adapter))
 }
 
+/// Creates a 2D color target for render tests.
+///
+/// The texture has `RENDER_ATTACHMENT` and `COPY_SRC` usage, so tests can
+/// render into it and read the result back with `capture_texture`.
+#[allow(dead_code)]
+pub fn create_target(
+    device: &Device,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Test Target"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
 /// Creates a simple WGSL shader source for testing.
 ///
 /// Returns a basic vertex and fragment shader that can be used for
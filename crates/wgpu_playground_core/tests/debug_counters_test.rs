@@ -0,0 +1,179 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::debug_counters::{instrument, DebugCounters};
+use wgpu_playground_core::debug_counters_panel::DebugCountersPanel;
+
+const SHADER: &str = r#"
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    debug_count("invocations");
+    if id.x % 4u == 0u {
+        debug_count_by("quarter", 3u);
+    }
+}
+"#;
+
+/// Instrument [`SHADER`] and build its pipeline with the debug buffer at
+/// group 0, binding 0
+fn create_pipeline(
+    device: &wgpu::Device,
+    counters: &mut DebugCounters,
+) -> (wgpu::ComputePipeline, wgpu::BindGroup) {
+    let shader = instrument(SHADER, 0, 0).expect("shader instruments");
+    counters.set_counters(shader.counters);
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Counted Shader"),
+        source: wgpu::ShaderSource::Wgsl(shader.source.into()),
+    });
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[DebugCounters::layout_entry(0, wgpu::ShaderStages::COMPUTE)],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[Some(&layout)],
+        immediate_size: 0,
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: None,
+        layout: Some(&pipeline_layout),
+        module: &module,
+        entry_point: Some("main"),
+        compilation_options: Default::default(),
+        cache: None,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &layout,
+        entries: &[counters.bind_group_entry(0)],
+    });
+    (pipeline, bind_group)
+}
+
+fn run_frame(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    counters: &mut DebugCounters,
+    pipeline: &wgpu::ComputePipeline,
+    bind_group: &wgpu::BindGroup,
+    workgroups: u32,
+) {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    counters.begin_frame(&mut encoder);
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.dispatch_workgroups(workgroups, 1, 1);
+    }
+    counters.end_frame(&mut encoder);
+    queue.submit(std::iter::once(encoder.finish()));
+    counters.frame_submitted();
+}
+
+#[test]
+fn test_debug_counters_count_invocations() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut counters = DebugCounters::new(&device);
+        let (pipeline, bind_group) = create_pipeline(&device, &mut counters);
+        assert_eq!(counters.counters(), ["invocations", "quarter"]);
+
+        run_frame(&device, &queue, &mut counters, &pipeline, &bind_group, 4);
+        assert!(counters.wait(&device), "readback arrives");
+        assert_eq!(counters.latest(), Some(&[256, 64 * 3][..]));
+    });
+}
+
+#[test]
+fn test_debug_counters_reset_each_frame() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut counters = DebugCounters::new(&device);
+        let (pipeline, bind_group) = create_pipeline(&device, &mut counters);
+
+        for workgroups in [1, 2, 3] {
+            run_frame(
+                &device,
+                &queue,
+                &mut counters,
+                &pipeline,
+                &bind_group,
+                workgroups,
+            );
+            assert!(counters.wait(&device));
+        }
+        let invocations: Vec<u32> = counters.history().iter().map(|frame| frame[0]).collect();
+        assert_eq!(invocations, vec![64, 128, 192]);
+
+        let stats = counters.stats(0).unwrap();
+        assert_eq!((stats.min, stats.max, stats.last), (64, 192, 192));
+    });
+}
+
+#[test]
+fn test_debug_counters_skip_frames_while_reading_back() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut counters = DebugCounters::new(&device);
+        let (pipeline, bind_group) = create_pipeline(&device, &mut counters);
+
+        // The second frame is recorded before the first readback is collected
+        run_frame(&device, &queue, &mut counters, &pipeline, &bind_group, 1);
+        run_frame(&device, &queue, &mut counters, &pipeline, &bind_group, 2);
+        assert_eq!(counters.skipped_frames(), 1);
+        assert!(counters.wait(&device));
+        assert_eq!(counters.history().len(), 1);
+        assert_eq!(counters.latest().unwrap()[0], 64);
+    });
+}
+
+#[test]
+fn test_debug_counters_panel_runs_default_shader() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut panel = DebugCountersPanel::new();
+        panel.set_workgroups(2);
+        for _ in 0..4 {
+            panel.run_frame(&device, &queue);
+        }
+        assert!(panel.build_error().is_none(), "{:?}", panel.build_error());
+        let counters = panel.counters().expect("counters exist after a frame");
+        assert_eq!(counters.counters().len(), 4);
+
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("device polls");
+        panel.run_frame(&device, &queue);
+        let latest = panel
+            .counters()
+            .unwrap()
+            .latest()
+            .expect("a frame was read back");
+        // Every ray either hits or misses
+        assert_eq!(latest[0], 128);
+        assert_eq!(latest[1] + latest[2], latest[0]);
+
+        panel.set_source("@compute @workgroup_size(1) fn main() { debug_count(1u); }");
+        panel.run_frame(&device, &queue);
+        assert!(panel.build_error().is_some());
+    });
+}
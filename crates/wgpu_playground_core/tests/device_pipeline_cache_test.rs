@@ -0,0 +1,122 @@
+mod common;
+
+use common::{create_test_compute_shader_source, create_test_instance_and_adapter};
+use wgpu_playground_core::device_pipeline_cache::{
+    required_features, DevicePipelineCache, DevicePipelineCacheError,
+};
+
+async fn create_device(adapter: &wgpu::Adapter) -> Option<wgpu::Device> {
+    let (device, _queue) = adapter
+        .request_device(&wgpu::DeviceDescriptor {
+            required_features: required_features(adapter),
+            ..Default::default()
+        })
+        .await
+        .ok()?;
+    Some(device)
+}
+
+fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "wgpu_playground_pipeline_cache_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    cache: Option<&wgpu::PipelineCache>,
+) -> wgpu::ComputePipeline {
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Cached Shader"),
+        source: wgpu::ShaderSource::Wgsl(create_test_compute_shader_source().into()),
+    });
+    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("Cached Pipeline"),
+        layout: None,
+        module: &module,
+        entry_point: Some("main"),
+        compilation_options: Default::default(),
+        cache,
+    })
+}
+
+#[test]
+fn test_pipeline_cache_round_trip() {
+    pollster::block_on(async {
+        let Some((_instance, adapter)) = create_test_instance_and_adapter().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let Some(device) = create_device(&adapter).await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let dir = test_dir("round_trip");
+        let cache = DevicePipelineCache::new();
+
+        if required_features(&adapter).is_empty() {
+            assert!(matches!(
+                cache.open_in(&dir, &adapter, &device),
+                Err(DevicePipelineCacheError::Unsupported(_))
+            ));
+            assert!(cache.for_device(&device).is_none());
+            assert!(matches!(
+                cache.save(),
+                Err(DevicePipelineCacheError::NotOpen)
+            ));
+            return;
+        }
+
+        let info = cache.open_in(&dir, &adapter, &device).unwrap();
+        assert_eq!(info.loaded_bytes, 0);
+        assert!(info.path.starts_with(&dir));
+        create_pipeline(&device, cache.for_device(&device).as_ref());
+
+        let saved = cache.save().unwrap();
+        assert_eq!(std::fs::metadata(&info.path).unwrap().len(), saved as u64);
+
+        // A later launch loads what was saved
+        let reopened = DevicePipelineCache::new();
+        let info = reopened.open_in(&dir, &adapter, &device).unwrap();
+        assert_eq!(info.loaded_bytes, saved);
+
+        reopened.clear().unwrap();
+        assert!(!info.path.exists());
+        assert_eq!(reopened.info().unwrap().loaded_bytes, 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    });
+}
+
+#[test]
+fn test_pipeline_cache_is_only_used_on_its_device() {
+    pollster::block_on(async {
+        let Some((_instance, adapter)) = create_test_instance_and_adapter().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        if required_features(&adapter).is_empty() {
+            eprintln!("Skipping test: Pipeline caching not supported");
+            return;
+        }
+        let (Some(device), Some(other)) =
+            (create_device(&adapter).await, create_device(&adapter).await)
+        else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let dir = test_dir("device");
+        let cache = DevicePipelineCache::new();
+        cache.open_in(&dir, &adapter, &device).unwrap();
+
+        assert!(cache.for_device(&device).is_some());
+        assert!(cache.for_device(&other).is_none());
+        create_pipeline(&other, cache.for_device(&other).as_ref());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    });
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::buffer_panel::BufferPanel;
+use wgpu_playground_core::device_recovery::{
+    DeviceLossMonitor, ResourceRecreate, SIMULATED_LOSS_MESSAGE,
+};
+use wgpu_playground_core::error::DeviceLostReason;
+
+#[test]
+fn test_simulated_loss_is_reported_once() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let monitor = DeviceLossMonitor::new();
+        monitor.watch(&device);
+        assert!(!monitor.is_lost());
+
+        monitor.simulate(&device);
+        let _ = device.poll(wgpu::PollType::Poll);
+
+        let loss = monitor.take().expect("simulated loss is recorded");
+        assert!(loss.simulated);
+        assert_eq!(loss.reason, DeviceLostReason::Destroyed);
+        assert_eq!(loss.message, SIMULATED_LOSS_MESSAGE);
+        assert!(monitor.take().is_none());
+    });
+}
+
+#[test]
+fn test_replaced_device_loss_is_ignored() {
+    pollster::block_on(async {
+        let Some((old_device, _old_queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let monitor = DeviceLossMonitor::new();
+        monitor.watch(&old_device);
+        monitor.watch(&device);
+
+        // Losing the replaced device must not mark the new one as lost
+        old_device.destroy();
+        drop(old_device);
+        assert!(!monitor.is_lost());
+
+        // Panels keep working on the new device
+        let mut panel = BufferPanel::new();
+        panel.recreate_resources(&device, &queue);
+        assert!(panel.create_buffer(&device).is_some());
+    });
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::fluid::{FluidSim, FluidView};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const SIZE: u32 = 64;
+
+/// Summed brightness of the display in the columns `columns`
+fn brightness(image: &image::RgbaImage, columns: std::ops::Range<u32>) -> u32 {
+    columns
+        .flat_map(|x| (0..SIZE).map(move |y| (x, y)))
+        .map(|(x, y)| {
+            image.get_pixel(x, y).0[..3]
+                .iter()
+                .map(|&c| c as u32)
+                .sum::<u32>()
+        })
+        .sum()
+}
+
+#[test]
+fn test_drag_adds_dye_that_flows_with_it() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let mut sim = FluidSim::new(&device, wgpu::TextureFormat::Rgba8Unorm, 1.0);
+        assert_eq!(sim.view, FluidView::Dye);
+        sim.reset(&device, &queue);
+        assert_eq!(sim.pending_splats(), 0);
+
+        sim.drag([0.5, 0.5], [0.05, 0.0]);
+        sim.update(&queue, 0.016);
+        sim.render(&device, &queue, &view);
+        let first = capture_texture(&device, &queue, &target)
+            .await
+            .expect("display captures");
+        let center = first.get_pixel(SIZE / 2, SIZE / 2).0;
+        assert!(center[..3].iter().any(|&c| c > 100), "dye at the splat");
+        assert_eq!(first.get_pixel(0, 0).0[..3], [0, 0, 0], "no dye far away");
+
+        for _ in 0..30 {
+            sim.update(&queue, 0.016);
+            sim.render(&device, &queue, &view);
+        }
+        let later = capture_texture(&device, &queue, &target)
+            .await
+            .expect("display captures");
+        let half = SIZE / 2;
+        assert!(
+            brightness(&later, half..SIZE) > brightness(&later, 0..half),
+            "the dye moves with the drag"
+        );
+    });
+}
+
+#[test]
+fn test_reset_clears_the_fluid() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let mut sim = FluidSim::new(&device, wgpu::TextureFormat::Rgba8Unorm, 1.0);
+        // The starting splats
+        for _ in 0..5 {
+            sim.update(&queue, 0.016);
+            sim.render(&device, &queue, &view);
+        }
+        sim.reset(&device, &queue);
+        sim.update(&queue, 0.016);
+        sim.render(&device, &queue, &view);
+        let image = capture_texture(&device, &queue, &target)
+            .await
+            .expect("display captures");
+        assert_eq!(brightness(&image, 0..SIZE), 0);
+    });
+}
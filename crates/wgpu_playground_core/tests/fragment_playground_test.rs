@@ -0,0 +1,223 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::audio_input::AudioSource;
+use wgpu_playground_core::controller_input::{InputControl, InputEvent, InputMapping};
+use wgpu_playground_core::fragment_playground::FragmentPlayground;
+use wgpu_playground_core::sdf_gallery::SDF_GALLERY;
+use wgpu_playground_core::shadertoy_shim::SHADERTOY_TEMPLATE;
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const WIDTH: u32 = 32;
+const HEIGHT: u32 = 16;
+
+/// Draw a frame and read it back
+async fn render(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    playground: &mut FragmentPlayground,
+) -> image::RgbaImage {
+    playground.render(device, queue, WIDTH, HEIGHT);
+    assert_eq!(playground.error(), None);
+    capture_texture(device, queue, playground.texture().unwrap())
+        .await
+        .unwrap()
+}
+
+#[test]
+fn test_frag_coord_starts_at_the_bottom_left() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut playground = FragmentPlayground::new();
+        playground.set_source(
+            "fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {\n\
+                 return vec4<f32>(step(iResolution.xy * 0.5, fragCoord), 0.0, 1.0);\n\
+             }",
+        );
+        let image = render(&device, &queue, &mut playground).await;
+        assert_eq!(image.dimensions(), (WIDTH, HEIGHT));
+        assert_eq!(image.get_pixel(0, HEIGHT - 1).0, [0, 0, 0, 255]);
+        assert_eq!(image.get_pixel(WIDTH - 1, HEIGHT - 1).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(0, 0).0, [0, 255, 0, 255]);
+    });
+}
+
+#[test]
+fn test_inputs_advance_every_frame() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut playground = FragmentPlayground::new();
+        playground.set_source(
+            "fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {\n\
+                 let frame = select(0.0, 1.0, iFrame == 1);\n\
+                 let clicked = select(0.0, 1.0, iMouse.w > 0.0 && iMouse.x == 3.0);\n\
+                 return vec4<f32>(frame, clicked, step(0.5, iTime), 1.0);\n\
+             }",
+        );
+        let pixel = |image: &image::RgbaImage| image.get_pixel(0, 0).0;
+
+        assert_eq!(
+            pixel(&render(&device, &queue, &mut playground).await),
+            [0, 0, 0, 255]
+        );
+        playground.advance(1.0);
+        playground.update_mouse(Some([3.0, 4.0]));
+        let second = render(&device, &queue, &mut playground).await;
+        assert_eq!(pixel(&second), [255, 255, 255, 255]);
+
+        // A paused clock holds the frame
+        playground.paused = true;
+        playground.advance(1.0);
+        playground.update_mouse(Some([3.0, 4.0]));
+        assert_eq!(
+            pixel(&render(&device, &queue, &mut playground).await),
+            [0, 0, 255, 255]
+        );
+        assert_eq!(playground.frame(), 2);
+    });
+}
+
+#[test]
+fn test_translated_shadertoy_and_own_uniforms() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut playground = FragmentPlayground::new();
+        assert!(playground.load_glsl(SHADERTOY_TEMPLATE).is_empty());
+        let image = render(&device, &queue, &mut playground).await;
+        // The template's gradient runs across the image
+        assert_ne!(image.get_pixel(0, 0), image.get_pixel(WIDTH - 1, 0));
+
+        // Uniforms after the inputs are bound too; colors start white
+        playground.set_source(
+            "@group(1) @binding(0) var<uniform> tint_color: vec3<f32>;\n\
+             fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {\n\
+                 return vec4<f32>(tint_color, 1.0);\n\
+             }",
+        );
+        let image = render(&device, &queue, &mut playground).await;
+        assert_eq!(image.get_pixel(WIDTH / 2, HEIGHT / 2).0, [255; 4]);
+    });
+}
+
+#[test]
+fn test_audio_reaches_the_shader() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut playground = FragmentPlayground::new();
+        playground.set_source(
+            "fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {\n\
+                 return vec4<f32>(audioBand(0), iAudio.y, step(0.5, iBeat), 1.0);\n\
+             }",
+        );
+        let pixel = |image: &image::RgbaImage| image.get_pixel(0, 0).0;
+        assert_eq!(
+            pixel(&render(&device, &queue, &mut playground).await),
+            [0, 0, 0, 255]
+        );
+
+        // Run the test signal up to its second kick
+        playground
+            .audio
+            .set_source(AudioSource::TestSignal)
+            .unwrap();
+        for _ in 0..60 {
+            playground.advance(1.0 / 60.0);
+            if playground.audio.features().beat == 1.0 {
+                break;
+            }
+        }
+        let [bass, summary, beat, _] = pixel(&render(&device, &queue, &mut playground).await);
+        assert!(
+            bass > 200 && summary > 200,
+            "bass {bass}, iAudio.y {summary}"
+        );
+        assert_eq!(beat, 255);
+    });
+}
+
+#[test]
+fn test_controller_drives_a_uniform() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut playground = FragmentPlayground::new();
+        playground.set_source(
+            "@group(1) @binding(0) var<uniform> tint_color: vec3<f32>;\n\
+             fn mainImage(fragCoord: vec2<f32>) -> vec4<f32> {\n\
+                 return vec4<f32>(tint_color, 1.0);\n\
+             }",
+        );
+        let knob = InputControl::MidiCc {
+            channel: 0,
+            controller: 1,
+        };
+        playground
+            .controllers
+            .mapper
+            .add(InputMapping::new(Some(knob), "tint_color").with_component(1));
+        let pixel = |image: &image::RgbaImage| image.get_pixel(0, 0).0;
+        // Unmoved, the mapping leaves the widget's white alone
+        assert_eq!(
+            pixel(&render(&device, &queue, &mut playground).await),
+            [255; 4]
+        );
+
+        playground.controllers.mapper.handle(InputEvent {
+            control: knob,
+            value: 0.0,
+        });
+        assert_eq!(
+            pixel(&render(&device, &queue, &mut playground).await),
+            [255, 0, 255, 255]
+        );
+    });
+}
+
+#[test]
+fn test_sdf_gallery_scenes_draw_with_their_parameters() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut playground = FragmentPlayground::new();
+        for example in SDF_GALLERY {
+            playground.load_gallery_example(example);
+            let image = render(&device, &queue, &mut playground).await;
+            for param in example.params {
+                let value = playground.uniform(param.name).unwrap()[0];
+                assert_eq!(value, param.default, "{}.{}", example.id, param.name);
+            }
+            let first = image.get_pixel(0, 0);
+            assert!(
+                image.pixels().any(|pixel| pixel != first),
+                "{} draws a scene",
+                example.id
+            );
+        }
+        assert_eq!(
+            playground.gallery_example().map(|example| example.id),
+            SDF_GALLERY.last().map(|example| example.id)
+        );
+    });
+}
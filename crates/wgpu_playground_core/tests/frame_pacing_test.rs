@@ -0,0 +1,54 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::frame_pacing::{FrameStats, FrameTimer, GpuLoad};
+
+#[test]
+fn test_timer_records_gpu_latency() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let load = GpuLoad::new(&device);
+        let mut timer = FrameTimer::new();
+        for _ in 0..4 {
+            timer.begin_frame();
+            timer.acquired();
+            load.submit(&device, &queue, 1_000);
+            timer.submitted(&queue);
+            // Waiting before the present still reaches the frame's sample
+            let _ = device.poll(wgpu::PollType::wait_indefinitely());
+            timer.presented();
+        }
+
+        let samples = timer.samples();
+        assert_eq!(samples.len(), 4);
+        for sample in &samples {
+            let latency = sample.latency.expect("work-done callback ran");
+            assert!(latency >= sample.cpu);
+        }
+        let stats = FrameStats::from_samples(&samples);
+        assert_eq!(stats.frames, 3);
+        assert!(stats.mean_latency.is_some());
+        assert!(stats.fps() > 0.0);
+    });
+}
+
+#[test]
+fn test_gpu_load_without_iterations() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let load = GpuLoad::new(&device);
+        load.submit(&device, &queue, 0);
+        let error = scope.pop().await;
+        assert!(error.is_none(), "unexpected error: {:?}", error);
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+    });
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::grass::{CoverageMode, GrassField};
+use wgpu_playground_core::math_utils::{look_at, mat4_mul, perspective, Mat4};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const SIZE: u32 = 128;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const SKY: wgpu::Color = wgpu::Color {
+    r: 0.0,
+    g: 0.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+/// A camera just above the grass, the blade tips against the sky
+fn view_proj() -> Mat4 {
+    let view = look_at([0.0, 0.4, 7.0], [0.0, 0.3, 0.0], [0.0, 1.0, 0.0]);
+    mat4_mul(&perspective(40.0_f32.to_radians(), 1.0, 0.1, 50.0), &view)
+}
+
+/// Pixels mixing blade and sky: neither the pure blue sky nor grass,
+/// which has little blue
+fn mixed_pixels(image: &image::RgbaImage) -> usize {
+    image
+        .pixels()
+        .filter(|p| (100..230).contains(&p[2]))
+        .count()
+}
+
+#[test]
+fn test_alpha_to_coverage_smooths_blade_edges() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut grass = GrassField::new(&device, FORMAT, SIZE, SIZE);
+        grass.set_blade_count(4000);
+
+        let mut images = Vec::new();
+        for coverage in [CoverageMode::AlphaToCoverage, CoverageMode::AlphaTest] {
+            grass.coverage = coverage;
+            grass.update(&queue, view_proj(), 1.0);
+            grass.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+            images.push(capture_texture(&device, &queue, &target).await.unwrap());
+        }
+
+        let grass_pixels = images[0].pixels().filter(|p| p[1] > p[2]).count();
+        assert!(grass_pixels > 0, "blades are visible");
+        let (smooth, aliased) = (mixed_pixels(&images[0]), mixed_pixels(&images[1]));
+        assert!(
+            smooth > aliased,
+            "alpha to coverage blends more edge pixels ({smooth} vs {aliased})"
+        );
+    });
+}
+
+#[test]
+fn test_wind_moves_the_blades() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut grass = GrassField::new(&device, FORMAT, SIZE, SIZE);
+        assert_eq!(grass.triangles(), grass.blade_count() * 8);
+
+        let mut render_at = |time| {
+            grass.update(&queue, view_proj(), time);
+            grass.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+        };
+        render_at(0.0);
+        let still = capture_texture(&device, &queue, &target).await.unwrap();
+        render_at(0.0);
+        let same = capture_texture(&device, &queue, &target).await.unwrap();
+        render_at(0.5);
+        let moved = capture_texture(&device, &queue, &target).await.unwrap();
+        assert_eq!(still, same, "generation is deterministic");
+        assert_ne!(still, moved);
+
+        // Without wind the time no longer matters
+        grass.wind_strength = 0.0;
+        grass.update(&queue, view_proj(), 0.0);
+        grass.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+        let calm = capture_texture(&device, &queue, &target).await.unwrap();
+        grass.update(&queue, view_proj(), 3.0);
+        grass.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+        assert_eq!(
+            calm,
+            capture_texture(&device, &queue, &target).await.unwrap()
+        );
+    });
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::{create_target, create_test_device, create_test_instance_and_adapter};
+use wgpu_playground_core::camera::Camera;
+use wgpu_playground_core::hardware_ray_tracing::{
+    adapter_supports_ray_queries, check_support, request_ray_queries,
+    AccelerationStructureBuilder, HardwareRayTracingError, Instance, RayQueryScene, RayQueryView,
+};
+use wgpu_playground_core::math_utils::MAT4_IDENTITY;
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 48;
+
+#[test]
+fn test_device_without_ray_queries_reports_fallback() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        // The default test device never opts into experimental features
+        let error = check_support(&device).unwrap_err();
+        assert!(matches!(error, HardwareRayTracingError::Unsupported { .. }));
+        assert!(error.to_string().contains("Compute Ray Tracer"));
+
+        let mut builder = AccelerationStructureBuilder::new();
+        let mesh = builder.add_mesh(&[[0.0; 3], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], &[0, 1, 2]);
+        builder.add_instance(Instance::new(mesh, MAT4_IDENTITY));
+        assert_eq!(builder.build(&device, &queue).err(), Some(error.clone()));
+        assert_eq!(
+            RayQueryScene::new(&device, &queue, wgpu::TextureFormat::Rgba8Unorm).err(),
+            Some(error)
+        );
+    });
+}
+
+#[test]
+fn test_ray_query_scene_renders_when_supported() {
+    pollster::block_on(async {
+        let Some((_instance, adapter)) = create_test_instance_and_adapter().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        if !adapter_supports_ray_queries(&adapter) {
+            eprintln!("Skipping test: Adapter has no hardware ray queries");
+            return;
+        }
+        let mut descriptor = wgpu::DeviceDescriptor::default();
+        request_ray_queries(&adapter, &mut descriptor);
+        let (device, queue) = adapter
+            .request_device(&descriptor)
+            .await
+            .expect("device with ray queries");
+        check_support(&device).expect("ray queries are enabled");
+
+        let target = create_target(&device, WIDTH, HEIGHT, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let mut scene = RayQueryScene::new(&device, &queue, wgpu::TextureFormat::Rgba8Unorm)
+            .expect("scene builds");
+        scene.settings.view = RayQueryView::Instances;
+        let camera = Camera::framing([0.0, 1.0, 0.0], 4.0);
+        let aspect = WIDTH as f32 / HEIGHT as f32;
+
+        scene.update(camera.view_projection(aspect), camera.eye(), 0.0);
+        scene.render(&device, &queue, &view);
+        assert_eq!(scene.tlas_builds(), 1);
+        // Moving instances rebuild the TLAS
+        scene.update(camera.view_projection(aspect), camera.eye(), 0.1);
+        scene.render(&device, &queue, &view);
+        assert_eq!(scene.tlas_builds(), 2);
+
+        let image = capture_texture(&device, &queue, &target)
+            .await
+            .expect("image captures");
+        let mut colors: Vec<_> = image.pixels().map(|p| p.0).collect();
+        colors.sort_unstable();
+        colors.dedup();
+        // Several instances and the sky
+        assert!(colors.len() >= 4, "{} colors", colors.len());
+    });
+}
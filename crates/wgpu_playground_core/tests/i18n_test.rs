@@ -0,0 +1,146 @@
+//! Tests for the message catalogs shipped with the crate
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use wgpu_playground_core::i18n::{self, Translator, FALLBACK_LANGUAGE};
+
+/// Names of the `{ $name }` placeables of a message
+fn placeables(message: &str) -> BTreeSet<&str> {
+    message
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .filter_map(|(placeable, _)| placeable.trim().strip_prefix('$'))
+        .map(str::trim)
+        .collect()
+}
+
+/// Keys passed as string literals to `tr` and `tr_args` in the Rust files of a directory
+fn keys_used_in(dir: &Path, keys: &mut BTreeSet<String>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            keys_used_in(&path, keys);
+            continue;
+        }
+        if path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        for call in ["tr(", "tr_args(", "TooltipInfo::new("] {
+            for (start, _) in source.match_indices(call) {
+                let before = source[..start].chars().next_back();
+                if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let rest = source[start + call.len()..].trim_start();
+                if let Some(literal) = rest.strip_prefix('"') {
+                    let key = &literal[..literal.find('"').unwrap()];
+                    keys.insert(key.to_string());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_builtin_catalogs_name_their_language() {
+    let translator = Translator::new();
+    let languages = translator.languages();
+    assert_eq!(languages[0].code, FALLBACK_LANGUAGE);
+    assert_eq!(languages[0].coverage, 1.0);
+    for language in &languages {
+        assert_ne!(
+            language.name, language.code,
+            "{} has no language-name",
+            language.code
+        );
+        assert!(language.coverage > 0.0, "{}", language.code);
+    }
+}
+
+#[test]
+fn test_translations_match_the_english_messages() {
+    let translator = Translator::new();
+    let english = translator.catalog(FALLBACK_LANGUAGE).unwrap();
+    for language in translator.languages() {
+        let catalog = translator.catalog(&language.code).unwrap();
+        for key in catalog.keys() {
+            let Some(source) = english.get(key) else {
+                panic!("{}: {} is not an English message", language.code, key);
+            };
+            assert_eq!(
+                placeables(catalog.get(key).unwrap()),
+                placeables(source),
+                "{}: placeables of {}",
+                language.code,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn test_every_key_used_in_the_code_is_defined() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut keys = BTreeSet::new();
+    keys_used_in(&manifest_dir.join("src"), &mut keys);
+    let gui_src = manifest_dir.join("../wgpu_playground_gui/src");
+    if gui_src.is_dir() {
+        keys_used_in(&gui_src, &mut keys);
+    }
+    assert!(keys.contains("tooltip-buffer-usage-vertex"));
+
+    let translator = Translator::new();
+    let english = translator.catalog(FALLBACK_LANGUAGE).unwrap();
+    let missing: Vec<_> = keys
+        .iter()
+        .filter(|key| english.get(key).is_none())
+        .collect();
+    assert!(missing.is_empty(), "Undefined messages: {:?}", missing);
+}
+
+#[test]
+fn test_untranslated_messages_fall_back_to_english() {
+    let mut translator = Translator::new();
+    for language in translator.languages() {
+        let missing: Vec<String> = translator
+            .missing_keys(&language.code)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        // Shown with `--nocapture`, as a to-do list for translators
+        println!(
+            "{}: {} untranslated messages: {:?}",
+            language.code,
+            missing.len(),
+            missing
+        );
+
+        assert!(translator.set_language(&language.code));
+        let english = translator.catalog(FALLBACK_LANGUAGE).unwrap();
+        for key in &missing {
+            assert_eq!(
+                translator.translate(key),
+                english.get(key).unwrap(),
+                "{}: {}",
+                language.code,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn test_selected_language_is_used_by_tr() {
+    assert!(i18n::set_language("de"));
+    assert_eq!(i18n::language(), "de");
+    assert_eq!(i18n::tr("nav-settings"), "Einstellungen");
+    assert_eq!(
+        i18n::tr_args("settings-cache-limit-set", &[("mib", &64)]),
+        "✓ Cache-Limit auf 64 MiB gesetzt"
+    );
+    assert!(!i18n::set_language("xx"));
+    assert!(i18n::set_language(FALLBACK_LANGUAGE));
+    assert_eq!(i18n::tr("nav-settings"), "Settings");
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::image_processing::{
+    test_pattern, FilterParams, ImageFilter, ImageProcessor,
+};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+/// A `size`x`size` image, dark on the left half and bright on the right
+fn step_image(size: u32, dark: u8, bright: u8) -> Vec<u8> {
+    (0..size * size)
+        .flat_map(|i| {
+            let value = if i % size < size / 2 { dark } else { bright };
+            [value, value, value, 255]
+        })
+        .collect()
+}
+
+#[test]
+fn test_gaussian_blur_softens_edge() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut processor = ImageProcessor::new(&device);
+        processor
+            .set_image(&device, &queue, &step_image(32, 0, 255), 32, 32)
+            .expect("image uploads");
+        processor
+            .process(
+                &device,
+                &queue,
+                ImageFilter::GaussianBlur,
+                &FilterParams::default(),
+            )
+            .expect("filter runs");
+
+        let output = capture_texture(&device, &queue, processor.output_texture().unwrap())
+            .await
+            .expect("output captures");
+        // Far from the edge the image is unchanged, next to it values mix
+        assert!(output.get_pixel(0, 16)[0] < 8);
+        assert!(output.get_pixel(31, 16)[0] > 247);
+        let left = output.get_pixel(15, 16)[0];
+        let right = output.get_pixel(16, 16)[0];
+        assert!((60..196).contains(&left), "left of edge: {}", left);
+        assert!((60..196).contains(&right), "right of edge: {}", right);
+        assert!(left < right);
+    });
+}
+
+#[test]
+fn test_sobel_finds_edge() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut processor = ImageProcessor::new(&device);
+        processor
+            .set_image(&device, &queue, &step_image(16, 0, 255), 16, 16)
+            .expect("image uploads");
+        processor
+            .process(
+                &device,
+                &queue,
+                ImageFilter::SobelEdges,
+                &FilterParams::default(),
+            )
+            .expect("filter runs");
+
+        let output = capture_texture(&device, &queue, processor.output_texture().unwrap())
+            .await
+            .expect("output captures");
+        assert_eq!(output.get_pixel(2, 8)[0], 0, "flat areas have no edges");
+        assert_eq!(output.get_pixel(13, 8)[0], 0);
+        assert_eq!(output.get_pixel(8, 8)[0], 255, "the step is an edge");
+    });
+}
+
+#[test]
+fn test_histogram_equalization_stretches_contrast() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut processor = ImageProcessor::new(&device);
+        processor
+            .set_image(&device, &queue, &step_image(16, 100, 140), 16, 16)
+            .expect("image uploads");
+        processor
+            .process(
+                &device,
+                &queue,
+                ImageFilter::HistogramEqualization,
+                &FilterParams::default(),
+            )
+            .expect("filter runs");
+
+        let output = capture_texture(&device, &queue, processor.output_texture().unwrap())
+            .await
+            .expect("output captures");
+        // The darker half is the lowest luminance present and maps to black
+        assert!(output.get_pixel(0, 0)[0] < 4);
+        assert!(output.get_pixel(15, 0)[0] > 251);
+
+        // With an amount of zero the original comes through
+        let params = FilterParams {
+            equalize_amount: 0.0,
+            ..FilterParams::default()
+        };
+        processor
+            .process(&device, &queue, ImageFilter::HistogramEqualization, &params)
+            .expect("filter runs");
+        let output = capture_texture(&device, &queue, processor.output_texture().unwrap())
+            .await
+            .expect("output captures");
+        assert!((99..=101).contains(&output.get_pixel(0, 0)[0]));
+    });
+}
+
+#[test]
+fn test_image_processor_validates_input() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut processor = ImageProcessor::new(&device);
+        assert!(processor
+            .process(
+                &device,
+                &queue,
+                ImageFilter::SobelEdges,
+                &FilterParams::default()
+            )
+            .is_err());
+        assert!(processor
+            .set_image(&device, &queue, &[0; 12], 2, 2)
+            .is_err());
+        assert!(processor.set_image(&device, &queue, &[], 0, 0).is_err());
+
+        processor
+            .set_image(&device, &queue, &test_pattern(20, 10), 20, 10)
+            .expect("image uploads");
+        assert_eq!(processor.size(), Some((20, 10)));
+    });
+}
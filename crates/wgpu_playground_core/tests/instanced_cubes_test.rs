@@ -0,0 +1,76 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::instanced_cubes::{grid_radius, DrawPath, InstancedCubes};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const SIZE: u32 = 128;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// An orthographic view-projection looking down -Z that squeezes the
+/// whole grid into clip space
+fn fit_grid(count: u32) -> [[f32; 4]; 4] {
+    let s = 1.0 / grid_radius(count);
+    [
+        [s, 0.0, 0.0, 0.0],
+        [0.0, s, 0.0, 0.0],
+        [0.0, 0.0, -s * 0.5, 0.0],
+        [0.0, 0.0, 0.5, 1.0],
+    ]
+}
+
+#[test]
+fn test_instanced_and_per_object_paths_match() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut cubes = InstancedCubes::new(&device, &queue, FORMAT, SIZE, SIZE);
+        cubes.set_instance_count(&queue, 64);
+        cubes.update(&queue, fit_grid(64), 0.5);
+
+        let mut images = Vec::new();
+        for path in [DrawPath::Instanced, DrawPath::PerObject] {
+            cubes.set_path(path);
+            cubes.render(&device, &queue, &view, (SIZE, SIZE), wgpu::Color::BLACK);
+            assert!(cubes.frame_time(path).is_some());
+            images.push(capture_texture(&device, &queue, &target).await.unwrap());
+        }
+        assert_eq!(cubes.draw_calls(), 64);
+
+        let covered = images[0].pixels().filter(|p| p[0] > 0).count();
+        assert!(covered > 0, "cubes are visible");
+        assert_eq!(images[0], images[1], "both paths draw the same image");
+    });
+}
+
+#[test]
+fn test_instance_count_controls_coverage() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut cubes = InstancedCubes::new(&device, &queue, FORMAT, SIZE, SIZE);
+        // Keep the camera fixed; the first cubes sit side by side in one layer
+        cubes.update(&queue, fit_grid(27), 0.0);
+
+        let mut coverage = Vec::new();
+        for count in [1, 2, 3] {
+            cubes.set_instance_count(&queue, count);
+            assert_eq!(cubes.instance_count(), count);
+            cubes.render(&device, &queue, &view, (SIZE, SIZE), wgpu::Color::BLACK);
+            let image = capture_texture(&device, &queue, &target).await.unwrap();
+            coverage.push(image.pixels().filter(|p| p[0] > 0).count());
+        }
+        assert!(coverage[0] > 0);
+        assert!(coverage[0] < coverage[1] && coverage[1] < coverage[2]);
+    });
+}
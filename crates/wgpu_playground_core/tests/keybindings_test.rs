@@ -0,0 +1,160 @@
+use egui::Key;
+use serial_test::serial;
+use wgpu_playground_core::keybindings::{
+    config_dir, default_path, Action, KeyBindings, Shortcut, CONFIG_DIR_ENV, KEYBINDINGS_FILE,
+};
+
+fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "wgpu_playground_keybindings_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Actions the bindings trigger for one frame of input `events`
+fn triggered(bindings: &KeyBindings, events: Vec<egui::Event>) -> Vec<Action> {
+    let ctx = egui::Context::default();
+    ctx.begin_pass(egui::RawInput {
+        events,
+        ..Default::default()
+    });
+    let actions = ctx.input(|i| bindings.triggered(i));
+    let _ = ctx.end_pass();
+    actions
+}
+
+fn press(key: Key, modifiers: egui::Modifiers) -> egui::Event {
+    egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    }
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let dir = test_dir("round_trip");
+    let path = dir.join("nested").join(KEYBINDINGS_FILE);
+
+    let mut bindings = KeyBindings::new();
+    bindings.set(Action::CompileShader, vec![Shortcut::command(Key::B)]);
+    bindings.add(Action::ShowConsole, Shortcut::key(Key::F2));
+    bindings.save_to(&path).unwrap();
+
+    let loaded = KeyBindings::load_from(&path).unwrap();
+    assert_eq!(loaded, bindings);
+    assert_eq!(
+        loaded.shortcuts(Action::CompileShader),
+        &[Shortcut::command(Key::B)]
+    );
+    assert!(loaded.is_default(Action::SaveState));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_missing_file_gives_defaults() {
+    let dir = test_dir("missing");
+    let loaded = KeyBindings::load_from(&dir.join(KEYBINDINGS_FILE)).unwrap();
+    assert_eq!(loaded, KeyBindings::new());
+}
+
+#[test]
+fn test_invalid_file_is_an_error() {
+    let dir = test_dir("invalid");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(KEYBINDINGS_FILE);
+    std::fs::write(&path, "{ not json").unwrap();
+    assert!(KeyBindings::load_from(&path).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+#[serial]
+fn test_config_dir_env_override() {
+    let dir = test_dir("env");
+    std::env::set_var(CONFIG_DIR_ENV, &dir);
+    assert_eq!(config_dir(), Some(dir.clone()));
+    assert_eq!(default_path(), Some(dir.join(KEYBINDINGS_FILE)));
+
+    let mut bindings = KeyBindings::new();
+    bindings.set(Action::TogglePreview, vec![Shortcut::key(Key::F8)]);
+    bindings.save().unwrap();
+    assert_eq!(KeyBindings::load().unwrap(), bindings);
+
+    std::env::remove_var(CONFIG_DIR_ENV);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_triggered_by_key_presses() {
+    let bindings = KeyBindings::new();
+    assert_eq!(
+        triggered(&bindings, vec![press(Key::F5, egui::Modifiers::NONE)]),
+        vec![Action::CompileShader]
+    );
+    assert_eq!(
+        triggered(&bindings, vec![press(Key::Num3, egui::Modifiers::COMMAND)]),
+        vec![Action::ShowBuffers]
+    );
+    // Shift makes Ctrl+Z a redo, not an undo as well
+    assert_eq!(
+        triggered(
+            &bindings,
+            vec![press(
+                Key::Z,
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT
+            )]
+        ),
+        vec![Action::Redo]
+    );
+    assert!(triggered(&bindings, vec![press(Key::N, egui::Modifiers::NONE)]).is_empty());
+}
+
+#[test]
+fn test_conflicting_shortcut_triggers_every_action() {
+    let mut bindings = KeyBindings::new();
+    bindings.set(Action::RunDispatch, vec![Shortcut::key(Key::F5)]);
+    assert_eq!(
+        bindings.conflicts(),
+        vec![(
+            Shortcut::key(Key::F5),
+            vec![Action::CompileShader, Action::RunDispatch]
+        )]
+    );
+    assert_eq!(
+        triggered(&bindings, vec![press(Key::F5, egui::Modifiers::NONE)]),
+        vec![Action::CompileShader, Action::RunDispatch]
+    );
+}
+
+#[test]
+fn test_reset_restores_defaults() {
+    let mut bindings = KeyBindings::new();
+    bindings.set(Action::Undo, Vec::new());
+    bindings.set(Action::Redo, Vec::new());
+    assert!(!bindings.is_default(Action::Undo));
+
+    bindings.reset(Action::Undo);
+    assert!(bindings.is_default(Action::Undo));
+    assert!(!bindings.is_default(Action::Redo));
+
+    bindings.reset_all();
+    assert_eq!(bindings, KeyBindings::new());
+}
+
+#[test]
+fn test_reserved_shortcuts() {
+    assert!(Shortcut::command(Key::C).reserved_by().is_some());
+    assert!(Shortcut::command(Key::Minus).reserved_by().is_some());
+    assert!(Shortcut::command(Key::C)
+        .with_shift()
+        .reserved_by()
+        .is_none());
+    assert!(Shortcut::key(Key::F5).reserved_by().is_none());
+}
@@ -0,0 +1,125 @@
+//! Tests that the lessons shipped with the crate can be completed
+
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use wgpu_playground_core::lesson::all_lessons;
+use wgpu_playground_core::project_template::ProjectTemplate;
+use wgpu_playground_core::state::PlaygroundState;
+use wgpu_playground_core::tutorial::TutorialState;
+use wgpu_playground_core::validation::{field_at, Assertion};
+
+fn start_state(lesson: &wgpu_playground_core::lesson::Lesson) -> PlaygroundState {
+    lesson
+        .start_template()
+        .unwrap_or_else(|| panic!("{} has no start template", lesson.id))
+        .state()
+}
+
+#[test]
+fn test_lessons_are_well_formed() {
+    let mut ids = std::collections::HashSet::new();
+    for lesson in all_lessons() {
+        assert!(ids.insert(lesson.id.clone()), "Duplicate {}", lesson.id);
+        assert!(!lesson.explanation.is_empty(), "{}", lesson.id);
+        assert!(!lesson.checks.is_empty(), "{}", lesson.id);
+        assert!(lesson.target.is_object(), "{}", lesson.id);
+
+        // Every check reads a field that exists once the lesson is solved
+        let solution = lesson.solution(&start_state(&lesson)).unwrap();
+        let state = serde_json::to_value(solution).unwrap();
+        for check in &lesson.checks {
+            let path = check.assertion.path().unwrap();
+            let field = field_at(&state, path);
+            match &check.assertion {
+                Assertion::Contains { .. } | Assertion::ShaderCompiles { .. } => assert!(
+                    field.is_some_and(|f| f.is_string()),
+                    "{}: {}",
+                    lesson.id,
+                    path
+                ),
+                _ => assert!(field.is_some(), "{}: {}", lesson.id, path),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lessons_start_unsolved() {
+    for lesson in all_lessons() {
+        let outcomes = lesson.evaluate(&start_state(&lesson));
+        assert!(
+            !outcomes.iter().all(|outcome| outcome.is_passed()),
+            "{} is solved before it starts",
+            lesson.id
+        );
+    }
+}
+
+#[test]
+fn test_solutions_pass_every_check() {
+    for lesson in all_lessons() {
+        // From the start state and from an unrelated project
+        for state in [
+            start_state(&lesson),
+            ProjectTemplate::ParticleSystem.state(),
+        ] {
+            let solution = lesson.solution(&state).unwrap();
+            let outcomes = lesson.evaluate(&solution);
+            assert!(
+                outcomes.iter().all(|outcome| outcome.is_passed()),
+                "{}: {:?}",
+                lesson.id,
+                outcomes
+            );
+        }
+    }
+}
+
+#[test]
+fn test_solution_shaders_validate() {
+    for lesson in all_lessons() {
+        let solution = lesson.solution(&start_state(&lesson)).unwrap();
+        let source = &solution.shader_editor.as_ref().unwrap().source_code;
+        let module = naga::front::wgsl::parse_str(source)
+            .unwrap_or_else(|e| panic!("{}: {}", lesson.id, e.emit_to_string(source)));
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .unwrap_or_else(|e| panic!("{}: {}", lesson.id, e.emit_to_string(source)));
+    }
+}
+
+#[test]
+fn test_solution_keeps_unrelated_panels() {
+    let lesson = all_lessons()
+        .into_iter()
+        .find(|lesson| lesson.id == "depth_testing")
+        .unwrap();
+    let state = ProjectTemplate::TexturedQuad.state();
+    let solution = lesson.solution(&state).unwrap();
+
+    assert_eq!(
+        solution.shader_editor.unwrap().source_code,
+        state.shader_editor.unwrap().source_code
+    );
+    assert_eq!(
+        solution.bind_group_panel.unwrap().entries,
+        state.bind_group_panel.unwrap().entries
+    );
+    let pipeline = solution.render_pipeline_panel.unwrap();
+    assert!(pipeline.enable_depth_stencil);
+    assert_eq!(
+        pipeline.vertex_entry_point,
+        state.render_pipeline_panel.unwrap().vertex_entry_point
+    );
+}
+
+#[test]
+fn test_tutorial_state_without_lessons_loads() {
+    // Saved before lessons existed
+    let state: TutorialState = serde_json::from_str(
+        r#"{"current_tutorial":null,"current_step":0,"completed_tutorials":["hello_triangle"],"visited_panels":[]}"#,
+    )
+    .unwrap();
+    assert_eq!(state.completed_tutorials, vec!["hello_triangle"]);
+    assert!(state.lessons.completed.is_empty());
+    assert!(state.lessons.current.is_none());
+}
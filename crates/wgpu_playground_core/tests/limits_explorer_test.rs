@@ -0,0 +1,51 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::limits_explorer::{LimitProbe, LimitsExplorer};
+
+#[test]
+fn test_probes_match_reported_limits() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let explorer = LimitsExplorer::new();
+        explorer.run_all(&device, &queue);
+
+        let results = explorer.results();
+        assert_eq!(results.len(), LimitProbe::ALL.len() * 2);
+        for result in &results {
+            assert!(result.passed(), "{}", result.summary());
+        }
+
+        // Values past the limit are rejected with the device's error
+        let past = results
+            .iter()
+            .find(|r| r.probe == LimitProbe::BindGroups && !r.expect_success)
+            .unwrap();
+        assert_eq!(past.value, u64::from(device.limits().max_bind_groups) + 1);
+        assert!(past.error.is_some());
+    });
+}
+
+#[test]
+fn test_probe_at_limit_only() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let mut explorer = LimitsExplorer::new();
+        explorer.probe_past_limit = false;
+        explorer.run(&device, &queue, LimitProbe::TextureDimension2d);
+
+        let results = explorer.results();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].expect_success);
+        assert!(results[0].error.is_none(), "{}", results[0].summary());
+
+        explorer.clear();
+        assert!(explorer.results().is_empty());
+    });
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::lod::{LodCounts, LodScene, LodView, SelectionPath, LOD_COUNT};
+use wgpu_playground_core::math_utils::{look_at, mat4_mul, perspective};
+
+const SIZE: u32 = 256;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// A camera above one corner of the grid, looking across it
+fn view() -> LodView {
+    let eye = [-40.0, 12.0, -40.0];
+    let fov_y = 60.0_f32.to_radians();
+    LodView {
+        view_proj: mat4_mul(
+            &perspective(fov_y, 1.0, 0.1, 300.0),
+            &look_at(eye, [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+        ),
+        eye,
+        fov_y,
+        viewport_height: SIZE,
+    }
+}
+
+/// Render one frame with `path` and return its counts
+fn counts(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    scene: &mut LodScene,
+    target: &wgpu::TextureView,
+    path: SelectionPath,
+) -> LodCounts {
+    scene.set_path(path);
+    scene.render(
+        device,
+        queue,
+        target,
+        (SIZE, SIZE),
+        &view(),
+        wgpu::Color::BLACK,
+    );
+    if path == SelectionPath::Gpu {
+        assert!(scene.wait_for_counts(device));
+    }
+    scene.counts().expect("counts after a frame")
+}
+
+#[test]
+fn test_gpu_selection_matches_cpu() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut scene = LodScene::new(&device, FORMAT, SIZE, SIZE);
+        let cpu = counts(&device, &queue, &mut scene, &target, SelectionPath::Cpu);
+        let gpu = counts(&device, &queue, &mut scene, &target, SelectionPath::Gpu);
+        assert_eq!(cpu, gpu);
+
+        let drawn: u32 = cpu.instances.iter().sum();
+        assert!(cpu.culled > 0, "objects behind the camera are culled");
+        assert_eq!(drawn + cpu.culled, scene.objects().len() as u32);
+        assert!(
+            cpu.instances.iter().filter(|&&count| count > 0).count() > 1,
+            "near and far objects use different meshes: {:?}",
+            cpu.instances
+        );
+    });
+}
+
+#[test]
+fn test_settings_change_selection() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut scene = LodScene::new(&device, FORMAT, SIZE, SIZE);
+        for path in [SelectionPath::Cpu, SelectionPath::Gpu] {
+            scene.settings.tolerance = 1.0;
+            scene.settings.cull = true;
+            scene.settings.forced_lod = None;
+            let strict = counts(&device, &queue, &mut scene, &target, path);
+
+            // A looser tolerance moves objects to coarser meshes
+            scene.settings.tolerance = 100.0;
+            let loose = counts(&device, &queue, &mut scene, &target, path);
+            assert_eq!(
+                loose.instances[LOD_COUNT - 1],
+                strict.instances.iter().sum::<u32>()
+            );
+            let (strict_triangles, loose_triangles) = (
+                strict.triangles(scene.meshes()).iter().sum::<u64>(),
+                loose.triangles(scene.meshes()).iter().sum::<u64>(),
+            );
+            assert!(loose_triangles < strict_triangles);
+
+            // Without culling every object is drawn, here with the finest mesh
+            scene.settings.cull = false;
+            scene.settings.forced_lod = Some(0);
+            let forced = counts(&device, &queue, &mut scene, &target, path);
+            assert_eq!(forced.culled, 0);
+            assert_eq!(forced.instances[0], scene.objects().len() as u32);
+        }
+    });
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::camera::Camera;
+use wgpu_playground_core::material::{GpuModel, Lighting, MaterialMap, PbrRenderer};
+use wgpu_playground_core::math_utils::MAT4_IDENTITY;
+use wgpu_playground_core::model_loader::{Material, Mesh, ModelData, ModelTexture, ModelVertex};
+use wgpu_playground_core::tangent_debug::{DebugView, TangentDebug};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const SIZE: u32 = 64;
+
+/// A 2×2 quad in the XY plane facing the default camera, with `material`
+fn quad(material: Material, textures: Vec<ModelTexture>) -> ModelData {
+    let normal = [0.0, 0.0, 1.0];
+    let vertices = vec![
+        ModelVertex::new([-1.0, -1.0, 0.0], normal, [0.0, 1.0]),
+        ModelVertex::new([1.0, -1.0, 0.0], normal, [1.0, 1.0]),
+        ModelVertex::new([1.0, 1.0, 0.0], normal, [1.0, 0.0]),
+        ModelVertex::new([-1.0, 1.0, 0.0], normal, [0.0, 0.0]),
+    ];
+    ModelData {
+        meshes: vec![Mesh {
+            vertices,
+            indices: vec![0, 1, 2, 0, 2, 3],
+            material_index: Some(0),
+        }],
+        materials: vec![material],
+        textures,
+        vertex_count: 4,
+        index_count: 6,
+    }
+}
+
+/// Render `model` and return the pixel at the center of the quad
+async fn render_center(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    model: &ModelData,
+    lighting: &Lighting,
+    edit: impl FnOnce(&mut GpuModel),
+) -> [u8; 4] {
+    render_with_debug(
+        device,
+        queue,
+        model,
+        lighting,
+        &TangentDebug::default(),
+        edit,
+    )
+    .await
+}
+
+/// Render `model` with the `debug` settings and return the pixel at the
+/// center of the quad
+async fn render_with_debug(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    model: &ModelData,
+    lighting: &Lighting,
+    debug: &TangentDebug,
+    edit: impl FnOnce(&mut GpuModel),
+) -> [u8; 4] {
+    let target = create_target(device, SIZE, SIZE, wgpu::TextureFormat::Rgba8UnormSrgb);
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut renderer = PbrRenderer::new(device, queue, wgpu::TextureFormat::Rgba8UnormSrgb);
+    let mut gpu_model = renderer.upload(device, queue, model).unwrap();
+    edit(&mut gpu_model);
+    renderer.update(queue, &Camera::new(), 1.0, &MAT4_IDENTITY, lighting, debug);
+    renderer.render(
+        device,
+        queue,
+        &gpu_model,
+        &view,
+        (SIZE, SIZE),
+        wgpu::Color::BLACK,
+    );
+    let image = capture_texture(device, queue, &target).await.unwrap();
+    image.get_pixel(SIZE / 2, SIZE / 2).0
+}
+
+fn luminance([r, g, b, _]: [u8; 4]) -> u32 {
+    r as u32 + g as u32 + b as u32
+}
+
+#[test]
+fn test_lit_quad_and_image_based_lighting() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let model = quad(Material::default(), Vec::new());
+        let direct_only = Lighting {
+            ibl: false,
+            ..Default::default()
+        };
+        let lit = render_center(&device, &queue, &model, &direct_only, |_| {}).await;
+        assert!(luminance(lit) > 60, "the light reaches the quad: {:?}", lit);
+
+        let with_ibl = render_center(&device, &queue, &model, &Lighting::default(), |_| {}).await;
+        assert!(
+            luminance(with_ibl) > luminance(lit),
+            "the environment adds light: {:?} vs {:?}",
+            with_ibl,
+            lit
+        );
+    });
+}
+
+#[test]
+fn test_base_color_map_is_sampled_and_can_be_disabled() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let red = ModelTexture {
+            width: 1,
+            height: 1,
+            rgba: vec![255, 0, 0, 255],
+        };
+        let material = Material {
+            base_color_texture: Some(0),
+            ..Default::default()
+        };
+        let model = quad(material, vec![red]);
+        let lighting = Lighting::default();
+
+        let [r, g, b, _] = render_center(&device, &queue, &model, &lighting, |_| {}).await;
+        assert!(
+            r.saturating_sub(g) > 40 && r.saturating_sub(b) > 40,
+            "red map: {:?}",
+            [r, g, b]
+        );
+
+        let [r, g, _, _] = render_center(&device, &queue, &model, &lighting, |model| {
+            model.materials[0].map_mut(MaterialMap::BaseColor).enabled = false;
+        })
+        .await;
+        assert!(r.abs_diff(g) < 40, "untextured white: {:?}", [r, g]);
+    });
+}
+
+#[test]
+fn test_debug_views_show_directions_as_colors() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let model = quad(Material::default(), Vec::new());
+        let lighting = Lighting::default();
+        // The quad faces +Z, with u along +X and v down the image
+        for (view, expected) in [
+            (DebugView::Normals, [128, 128, 255]),
+            (DebugView::Tangents, [255, 128, 128]),
+            (DebugView::Bitangents, [128, 255, 128]),
+            // Without a normal map the geometric normal is kept
+            (DebugView::MappedNormals, [128, 128, 255]),
+        ] {
+            let debug = TangentDebug {
+                view,
+                ..Default::default()
+            };
+            let [r, g, b, _] =
+                render_with_debug(&device, &queue, &model, &lighting, &debug, |_| {}).await;
+            assert!(
+                [r, g, b]
+                    .iter()
+                    .zip(expected)
+                    .all(|(&channel, expected)| channel.abs_diff(expected) <= 3),
+                "{:?}: {:?}",
+                view,
+                [r, g, b]
+            );
+        }
+    });
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::buffer_panel::BufferPanel;
+use wgpu_playground_core::memory_budget::{BudgetLevel, BudgetSource, MemoryBudget, UsageSource};
+
+const MIB: u64 = 1024 * 1024;
+
+#[test]
+fn test_refresh_measures_the_device() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut budget = MemoryBudget::new();
+        budget.refresh(&device, 4 * MIB);
+        let report = *budget.report().expect("a report after refreshing");
+        match report.usage_source {
+            // Without a figure from wgpu the tracked resources are used
+            UsageSource::Tracked => assert_eq!(report.used, 4 * MIB),
+            _ => assert!(report.used > 0),
+        }
+        assert_eq!(
+            report.budget.is_some(),
+            report.budget_source == Some(BudgetSource::Native)
+        );
+
+        budget.set_simulated_budget(Some(u64::MAX));
+        budget.refresh(&device, 4 * MIB);
+        let report = budget.report().unwrap();
+        assert_eq!(report.budget_source, Some(BudgetSource::Simulated));
+        assert_eq!(budget.level(), BudgetLevel::Normal);
+    });
+}
+
+#[test]
+fn test_simulated_budget_refuses_buffers() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut budget = MemoryBudget::new();
+        budget.set_simulated_budget(Some(16 * MIB));
+        assert_eq!(
+            budget.update(16 * MIB, UsageSource::Tracked, None),
+            Some(BudgetLevel::Exceeded)
+        );
+
+        let mut panel = BufferPanel::new();
+        panel.set_memory_budget(&budget);
+        assert!(panel.create_buffer(&device).is_none());
+
+        // Turning the simulation off lets the buffer through
+        budget.set_simulated_budget(None);
+        budget.update(16 * MIB, UsageSource::Tracked, None);
+        panel.set_memory_budget(&budget);
+        assert!(panel.create_buffer(&device).is_some());
+    });
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{create_target, create_test_device, create_test_instance_and_adapter};
+use wgpu_playground_core::camera::Camera;
+use wgpu_playground_core::mesh_shading::{
+    adapter_supports_mesh_shaders, check_support, request_mesh_shaders, torus_knot,
+    MeshShadingError, MeshletMesh, MeshletRenderer, MeshletSettings,
+};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 48;
+
+#[test]
+fn test_device_without_mesh_shaders_reports_fallback() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        // The default test device never opts into experimental features
+        let error = check_support(&device).unwrap_err();
+        assert!(matches!(error, MeshShadingError::Unsupported { .. }));
+
+        let (vertices, indices) = torus_knot();
+        let mesh = MeshletMesh::build(vertices, &indices);
+        let renderer = MeshletRenderer::new(
+            &device,
+            &mesh,
+            wgpu::TextureFormat::Rgba8Unorm,
+            WIDTH,
+            HEIGHT,
+        );
+        assert_eq!(renderer.err(), Some(error));
+    });
+}
+
+#[test]
+fn test_meshlets_render_when_supported() {
+    pollster::block_on(async {
+        let Some((_instance, adapter)) = create_test_instance_and_adapter().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        if !adapter_supports_mesh_shaders(&adapter) {
+            eprintln!("Skipping test: Adapter has no mesh shaders");
+            return;
+        }
+        let mut descriptor = wgpu::DeviceDescriptor::default();
+        request_mesh_shaders(&adapter, &mut descriptor);
+        let (device, queue) = adapter
+            .request_device(&descriptor)
+            .await
+            .expect("device with mesh shaders");
+        check_support(&device).expect("mesh shaders are enabled");
+
+        let target = create_target(&device, WIDTH, HEIGHT, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let (vertices, indices) = torus_knot();
+        let mesh = MeshletMesh::build(vertices, &indices);
+        let mut renderer = MeshletRenderer::new(
+            &device,
+            &mesh,
+            wgpu::TextureFormat::Rgba8Unorm,
+            WIDTH,
+            HEIGHT,
+        )
+        .expect("renderer builds");
+        let camera = Camera::framing([0.0; 3], 4.0);
+        renderer.update(
+            camera.view_projection(WIDTH as f32 / HEIGHT as f32),
+            camera.eye(),
+        );
+        renderer.render(
+            &device,
+            &queue,
+            &view,
+            (WIDTH, HEIGHT),
+            &MeshletSettings::default(),
+        );
+
+        let image = capture_texture(&device, &queue, &target)
+            .await
+            .expect("image captures");
+        let mut colors: Vec<_> = image.pixels().map(|p| p.0).collect();
+        colors.sort_unstable();
+        colors.dedup();
+        // Differently colored meshlets and the background
+        assert!(colors.len() >= 4, "{} colors", colors.len());
+    });
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::mip_debug::{
+    procedural_texture, MipDebugMode, MipDebugPreview, MIP_COLORS,
+};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+/// The mip level whose color is closest to an sRGB pixel
+fn level_of(pixel: [u8; 4]) -> usize {
+    let linear = pixel.map(|c| (c as f32 / 255.0).powf(2.2));
+    MIP_COLORS
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| {
+            let distance = |c: &[f32; 3]| (0..3).map(|i| (c[i] - linear[i]).powi(2)).sum::<f32>();
+            distance(a).total_cmp(&distance(b))
+        })
+        .map(|(level, _)| level)
+        .unwrap()
+}
+
+fn nearest_mip_sampler(device: &wgpu::Device, lod_min_clamp: f32) -> wgpu::Sampler {
+    device.create_sampler(&wgpu::SamplerDescriptor {
+        address_mode_u: wgpu::AddressMode::Repeat,
+        address_mode_v: wgpu::AddressMode::Repeat,
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        mipmap_filter: wgpu::MipmapFilterMode::Nearest,
+        lod_min_clamp,
+        ..Default::default()
+    })
+}
+
+/// Levels sampled at the bottom center, close to the camera, and just
+/// below the horizon
+async fn sampled_levels(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    preview: &MipDebugPreview,
+) -> (usize, usize) {
+    preview.render(device, queue);
+    let image = capture_texture(device, queue, preview.texture())
+        .await
+        .unwrap();
+    let (width, height) = preview.size();
+    let near = image.get_pixel(width / 2, height - 1).0;
+    // The horizon is at 20% of the height; stay clear of its haze
+    let far = image.get_pixel(width / 2, height * 24 / 100).0;
+    (level_of(near), level_of(far))
+}
+
+#[test]
+fn test_sampled_mip_level_follows_clamp_and_bias() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut preview = MipDebugPreview::new(&device);
+        preview.set_image(
+            &device,
+            &queue,
+            &procedural_texture(256, 256),
+            256,
+            256,
+            u32::MAX,
+        );
+        assert_eq!(preview.level_count(), 9);
+        preview.settings.mode = MipDebugMode::MipLevel;
+        preview.set_sampler(&device, nearest_mip_sampler(&device, 0.0), (0.0, 32.0));
+
+        let (near, far) = sampled_levels(&device, &queue, &preview).await;
+        assert_eq!(near, 0, "magnified texels come from the base level");
+        assert!(far >= 4, "the distance is minified, got level {}", far);
+
+        preview.settings.lod_bias = 4.0;
+        let (biased, _) = sampled_levels(&device, &queue, &preview).await;
+        assert!(biased >= 1, "a positive bias picks smaller levels");
+
+        preview.settings.lod_bias = 0.0;
+        preview.set_sampler(&device, nearest_mip_sampler(&device, 3.0), (3.0, 32.0));
+        let (clamped, _) = sampled_levels(&device, &queue, &preview).await;
+        assert_eq!(clamped, 3, "lod_min_clamp keeps the base levels out");
+    });
+}
+
+#[test]
+fn test_level_count_respects_the_limit() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut preview = MipDebugPreview::new(&device);
+        preview.set_image(&device, &queue, &procedural_texture(64, 64), 64, 64, 3);
+        assert_eq!(preview.level_count(), 3);
+
+        preview.settings.mode = MipDebugMode::MipLevel;
+        preview.set_sampler(&device, nearest_mip_sampler(&device, 0.0), (0.0, 32.0));
+        let (_, far) = sampled_levels(&device, &queue, &preview).await;
+        assert_eq!(far, 2, "sampling stops at the last level");
+    });
+}
@@ -94,3 +94,20 @@ fn test_missing_file() {
 
     assert!(result.is_err(), "Should fail for missing file");
 }
+
+#[test]
+#[serial]
+fn test_load_ply_cube_generates_normals() {
+    let cube_path = assets::models_dir().join("cube.ply");
+    let model = load_model_from_file(&cube_path).expect("Failed to load cube.ply");
+
+    // Six quads, each split into two triangles, over the eight corners
+    assert_eq!(model.index_count, 36);
+    assert_eq!(model.vertex_count, 8);
+
+    // The file has no normals; the generated ones point away from the center
+    for vertex in &model.meshes[0].vertices {
+        let outward: f32 = (0..3).map(|i| vertex.normal[i] * vertex.position[i]).sum();
+        assert!(outward > 0.0, "{:?}", vertex);
+    }
+}
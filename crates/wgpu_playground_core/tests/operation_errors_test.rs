@@ -0,0 +1,72 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::error::ErrorType;
+use wgpu_playground_core::operation_errors::OperationErrors;
+
+#[test]
+fn test_errors_are_attributed_to_operations() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let errors = OperationErrors::new();
+
+        errors.scoped(&device, "create_buffer", || {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 4,
+                // MAP_READ may only be combined with COPY_DST
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            })
+        });
+        errors.scoped(&device, "create_sampler", || {
+            device.create_sampler(&wgpu::SamplerDescriptor::default())
+        });
+
+        let error = errors
+            .get("create_buffer")
+            .expect("invalid usage is reported");
+        assert_eq!(error.error_type, ErrorType::Validation);
+        assert!(!errors.has_error("create_sampler"));
+
+        // A successful rerun clears the previous error
+        errors.scoped(&device, "create_buffer", || {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 4,
+                usage: wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        });
+        assert!(errors.is_empty());
+    });
+}
+
+#[test]
+fn test_scoped_errors_do_not_reach_uncaptured_handler() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+        let uncaptured = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = uncaptured.clone();
+        device.on_uncaptured_error(std::sync::Arc::new(move |_| {
+            flag.store(true, std::sync::atomic::Ordering::SeqCst);
+        }));
+
+        let errors = OperationErrors::new();
+        errors.scoped(&device, "compile", || {
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl("fn broken( {".into()),
+            })
+        });
+
+        assert!(errors.has_error("compile"));
+        assert!(!uncaptured.load(std::sync::atomic::Ordering::SeqCst));
+    });
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::parallel_algorithms::{
+    cpu_reference, random_input, Algorithm, AlgorithmParams, AlgorithmRunner, ReduceOp, Strategy,
+    MIN_ELEMENTS,
+};
+
+#[test]
+fn test_every_strategy_matches_the_cpu() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        // Not a multiple of the workgroup size, so the last block is partial
+        let input = random_input(70_001, 42);
+        let mut runner = AlgorithmRunner::new(&device);
+        runner
+            .set_input(&device, &queue, &input)
+            .expect("input uploads");
+
+        for &algorithm in Algorithm::all() {
+            for &op in ReduceOp::all() {
+                let expected = cpu_reference(algorithm, op, &input);
+                for &strategy in Strategy::all() {
+                    let run = runner
+                        .run(&device, &queue, algorithm, strategy, op)
+                        .expect("algorithm runs");
+                    assert!(
+                        run.output == expected,
+                        "{:?} {:?} {:?} differs from the CPU",
+                        algorithm,
+                        strategy,
+                        op
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[test]
+fn test_comparison_is_verified() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut runner = AlgorithmRunner::new(&device);
+        let params = AlgorithmParams {
+            element_count: 1 << 16,
+            op: ReduceOp::Sum,
+        };
+        let comparison = runner
+            .compare(&device, &queue, Algorithm::Reduction, params)
+            .expect("comparison runs");
+        assert!(comparison.verified());
+        assert_eq!(comparison.strategies.len(), Strategy::all().len());
+        assert_eq!(
+            comparison.result,
+            random_input(1 << 16, 1 << 16).iter().sum::<u32>()
+        );
+        assert!(comparison.speedup().is_some());
+    });
+}
+
+#[test]
+fn test_input_outside_the_range_is_rejected() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut runner = AlgorithmRunner::new(&device);
+        let too_few = vec![1; MIN_ELEMENTS as usize - 1];
+        assert!(runner.set_input(&device, &queue, &too_few).is_err());
+        assert_eq!(runner.element_count(), None);
+        assert!(runner
+            .run(
+                &device,
+                &queue,
+                Algorithm::Reduction,
+                Strategy::Workgroup,
+                ReduceOp::Sum
+            )
+            .is_err());
+    });
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::camera::Camera;
+use wgpu_playground_core::geometry::Primitive;
+use wgpu_playground_core::pipeline_preview::RenderPipelinePreviewState;
+use wgpu_playground_core::render_pipeline::{
+    DepthStencilState, MultisampleState, PipelineCache, PrimitiveState,
+};
+use wgpu_playground_core::scene::{Material, MeshRef, Scene, Transform};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+fn create_preview(device: &wgpu::Device) -> RenderPipelinePreviewState {
+    let mut preview = RenderPipelinePreviewState::new();
+    preview.initialize(device);
+    preview.update_pipeline(
+        device,
+        &PipelineCache::new(),
+        &PrimitiveState::default(),
+        Some(&DepthStencilState::new(wgpu::TextureFormat::Depth24Plus)),
+        None,
+        &MultisampleState::default(),
+    );
+    preview
+}
+
+/// Whether any pixel in the column strip `columns` differs from the
+/// background
+fn covered(image: &image::RgbaImage, columns: std::ops::Range<u32>) -> bool {
+    let background = *image.get_pixel(0, 0);
+    columns
+        .flat_map(|x| (0..image.height()).map(move |y| (x, y)))
+        .any(|(x, y)| *image.get_pixel(x, y) != background)
+}
+
+#[test]
+fn test_preview_draws_every_mesh_node() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut scene = Scene::empty();
+        let group = scene.add(None, "Group", None).unwrap();
+        for (name, x) in [("Left", -0.9), ("Right", 0.9)] {
+            let node = scene.add(Some(group), name, Some(MeshRef::Cube)).unwrap();
+            scene.get_mut(node).unwrap().transform = Transform::at([x, 0.0, 0.0]);
+        }
+        scene.get_mut(group).unwrap().transform.scale = [0.5; 3];
+
+        let mut preview = create_preview(&device);
+        preview.render(&device, &queue, 0.0, &Camera::new(), &scene);
+        let image = capture_texture(&device, &queue, preview.texture().unwrap())
+            .await
+            .unwrap();
+
+        let (width, _) = preview.size();
+        assert!(covered(&image, width / 4 - 8..width / 4 + 8));
+        assert!(covered(&image, width * 3 / 4 - 8..width * 3 / 4 + 8));
+        assert!(
+            !covered(&image, width / 2 - 2..width / 2 + 2),
+            "the gap between the scaled-down cubes stays empty"
+        );
+    });
+}
+
+#[test]
+fn test_material_tint_reaches_the_shader() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut scene = Scene::empty();
+        let node = scene.add(None, "Floor", Some(MeshRef::Plane)).unwrap();
+        let red = scene.add_material(Material::new("Red", [1.0, 0.0, 0.0, 1.0]));
+        scene.get_mut(node).unwrap().material = red;
+        // Stand the plane up to face the camera
+        scene.get_mut(node).unwrap().transform.rotation = [90.0, 0.0, 0.0];
+
+        let mut preview = create_preview(&device);
+        preview.render(&device, &queue, 0.0, &Camera::new(), &scene);
+        let image = capture_texture(&device, &queue, preview.texture().unwrap())
+            .await
+            .unwrap();
+
+        let (width, height) = preview.size();
+        let center = image.get_pixel(width / 2, height / 2);
+        assert!(center[0] > 100, "{:?}", center);
+        assert_eq!((center[1], center[2]), (0, 0), "{:?}", center);
+    });
+}
+
+#[test]
+fn test_preview_draws_generated_primitives() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let torus = Primitive::Torus {
+            tube_radius: 0.1,
+            segments: 64,
+            sides: 16,
+        };
+        let mut scene = Scene::empty();
+        let node = scene
+            .add(None, "Ring", Some(MeshRef::Primitive(torus)))
+            .unwrap();
+        // Face the camera with the hole
+        scene.get_mut(node).unwrap().transform.rotation = [90.0, 0.0, 0.0];
+
+        let mut preview = create_preview(&device);
+        preview.render(&device, &queue, 0.0, &Camera::new(), &scene);
+        let image = capture_texture(&device, &queue, preview.texture().unwrap())
+            .await
+            .unwrap();
+
+        let (width, height) = preview.size();
+        let background = *image.get_pixel(0, 0);
+        assert_eq!(*image.get_pixel(width / 2, height / 2), background);
+        assert!(covered(&image, width / 2 - 2..width / 2 + 2));
+
+        // Changing the parameters swaps in a new mesh: a sphere fills the hole
+        scene.get_mut(node).unwrap().mesh = Some(MeshRef::Primitive(Primitive::ALL[0]));
+        preview.render(&device, &queue, 0.0, &Camera::new(), &scene);
+        let image = capture_texture(&device, &queue, preview.texture().unwrap())
+            .await
+            .unwrap();
+        assert_ne!(*image.get_pixel(width / 2, height / 2), background);
+    });
+}
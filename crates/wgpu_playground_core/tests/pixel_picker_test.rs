@@ -0,0 +1,127 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::pixel_picker::{ChannelValue, PickError, PixelPicker};
+
+/// Upload a texture whose texel (x, y) holds the bytes [x, y, x + y, 255]
+fn create_gradient(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    width: u32,
+    height: u32,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Pixel Picker Test Texture"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: usage | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            data.extend_from_slice(&[x as u8, y as u8, (x + y) as u8, 255]);
+        }
+    }
+    queue.write_texture(
+        texture.as_image_copy(),
+        &data,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(width * 4),
+            rows_per_image: Some(height),
+        },
+        texture.size(),
+    );
+    texture
+}
+
+/// Pick `center` and wait until the copy has been collected
+fn pick_and_wait(
+    picker: &mut PixelPicker,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    center: (u32, u32),
+) {
+    picker.pick(device, queue, texture, center).unwrap();
+    assert!(picker.is_pending());
+    let _ = device.poll(wgpu::PollType::Wait {
+        submission_index: None,
+        timeout: None,
+    });
+    assert!(picker.collect(device).unwrap());
+    assert!(!picker.is_pending());
+}
+
+#[test]
+fn test_picks_region_around_cursor() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let texture = create_gradient(&device, &queue, 40, 30, wgpu::TextureUsages::COPY_SRC);
+        let mut picker = PixelPicker::new();
+        pick_and_wait(&mut picker, &device, &queue, &texture, (20, 10));
+
+        let region = picker.region().unwrap();
+        assert_eq!(
+            (region.origin, region.width, region.height),
+            ((18, 8), 5, 5)
+        );
+        assert_eq!(
+            region.center_texel().unwrap(),
+            vec![
+                ('R', ChannelValue::Unorm8(20)),
+                ('G', ChannelValue::Unorm8(10)),
+                ('B', ChannelValue::Unorm8(30)),
+                ('A', ChannelValue::Unorm8(255)),
+            ]
+        );
+        assert_eq!(region.rgba(22, 8).unwrap()[0], 22.0 / 255.0);
+
+        // At the corner the region is clipped to the texture
+        pick_and_wait(&mut picker, &device, &queue, &texture, (39, 0));
+        let region = picker.region().unwrap();
+        assert_eq!(
+            (region.origin, region.width, region.height),
+            ((37, 0), 3, 3)
+        );
+        assert_eq!(region.rgba(39, 2).unwrap()[2], 41.0 / 255.0);
+    });
+}
+
+#[test]
+fn test_rejects_textures_without_copy_src() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let texture = create_gradient(&device, &queue, 8, 8, wgpu::TextureUsages::TEXTURE_BINDING);
+        let mut picker = PixelPicker::new();
+        assert_eq!(
+            picker.pick(&device, &queue, &texture, (1, 1)),
+            Err(PickError::NotCopyable)
+        );
+        assert_eq!(picker.error(), Some(&PickError::NotCopyable));
+
+        let texture = create_gradient(&device, &queue, 8, 8, wgpu::TextureUsages::COPY_SRC);
+        assert_eq!(
+            picker.pick(&device, &queue, &texture, (8, 0)),
+            Err(PickError::OutOfBounds { x: 8, y: 0 })
+        );
+        assert!(!picker.is_pending());
+    });
+}
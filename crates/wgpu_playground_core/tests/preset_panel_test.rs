@@ -16,3 +16,19 @@ fn test_preset_panel_default() {
     // Default trait should work
     assert!(std::mem::size_of_val(&panel) > 0);
 }
+
+#[test]
+fn test_preset_panel_registers_commands() {
+    use wgpu_playground_core::command_palette::{CommandKind, CommandRegistry};
+    use wgpu_playground_core::preset::{get_all_presets, get_preset};
+
+    let mut registry = CommandRegistry::new();
+    PresetPanel::new().register_commands(&mut registry);
+    assert_eq!(registry.len(), get_all_presets().len());
+    for command in registry.commands() {
+        let CommandKind::ApplyPreset(id) = command.kind else {
+            panic!("unexpected command {:?}", command.kind);
+        };
+        assert!(get_preset(id).is_some());
+    }
+}
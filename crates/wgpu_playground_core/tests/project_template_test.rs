@@ -0,0 +1,153 @@
+//! Tests that the project templates shipped with the crate are coherent
+
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use naga::{AddressSpace, ShaderStage, StorageAccess};
+use wgpu_playground_core::code_generator::{CodeGenConfig, CodeGenerator, ExampleType};
+use wgpu_playground_core::project_template::ProjectTemplate;
+use wgpu_playground_core::state::PlaygroundState;
+
+fn shader_module(state: &PlaygroundState) -> naga::Module {
+    let source = &state.shader_editor.as_ref().unwrap().source_code;
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .unwrap_or_else(|e| panic!("{}", e.emit_to_string(source)));
+    module
+}
+
+fn has_entry_point(module: &naga::Module, name: &str, stage: ShaderStage) -> bool {
+    module
+        .entry_points
+        .iter()
+        .any(|entry| entry.name == name && entry.stage == stage)
+}
+
+#[test]
+fn test_every_template_loads() {
+    let mut names = std::collections::HashSet::new();
+    for template in ProjectTemplate::ALL {
+        assert!(
+            names.insert(template.name()),
+            "Duplicate {}",
+            template.name()
+        );
+        assert!(!template.description().is_empty());
+
+        let state = template.state();
+        assert!(state.shader_editor.is_some(), "{}", template.name());
+        assert!(state.buffer_panel.is_some(), "{}", template.name());
+        assert!(state.bind_group_panel.is_some(), "{}", template.name());
+        assert!(
+            state.render_pipeline_panel.is_some() || state.compute_pipeline_panel.is_some(),
+            "{} has no pipeline",
+            template.name()
+        );
+    }
+}
+
+#[test]
+fn test_template_shaders_match_pipelines() {
+    for template in ProjectTemplate::ALL {
+        let state = template.state();
+        let module = shader_module(&state);
+
+        if let Some(pipeline) = &state.render_pipeline_panel {
+            assert!(
+                has_entry_point(&module, &pipeline.vertex_entry_point, ShaderStage::Vertex),
+                "{}",
+                template.name()
+            );
+            assert!(
+                has_entry_point(
+                    &module,
+                    &pipeline.fragment_entry_point,
+                    ShaderStage::Fragment
+                ),
+                "{}",
+                template.name()
+            );
+        }
+        if let Some(pipeline) = &state.compute_pipeline_panel {
+            assert!(
+                has_entry_point(&module, &pipeline.entry_point, ShaderStage::Compute),
+                "{}",
+                template.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_template_bind_groups_match_shaders() {
+    for template in ProjectTemplate::ALL {
+        let state = template.state();
+        let module = shader_module(&state);
+        let entries = &state.bind_group_panel.as_ref().unwrap().entries;
+        assert_eq!(
+            entries,
+            &state.bind_group_layout_panel.as_ref().unwrap().entries,
+            "{}",
+            template.name()
+        );
+
+        let mut bindings = Vec::new();
+        for (_, var) in module.global_variables.iter() {
+            let Some(binding) = &var.binding else {
+                continue;
+            };
+            assert_eq!(binding.group, 0, "{}", template.name());
+            let binding_type = match var.space {
+                AddressSpace::Uniform => "UniformBuffer",
+                AddressSpace::Storage { access } => {
+                    assert_eq!(
+                        access.contains(StorageAccess::STORE),
+                        entries
+                            .iter()
+                            .any(|e| e.binding == binding.binding && !e.read_only),
+                        "{}",
+                        template.name()
+                    );
+                    "StorageBuffer"
+                }
+                _ => match module.types[var.ty].inner {
+                    naga::TypeInner::Sampler { .. } => "Sampler",
+                    _ => "Texture",
+                },
+            };
+            bindings.push((binding.binding, binding_type));
+        }
+        bindings.sort();
+
+        let configured: Vec<(u32, &str)> = entries
+            .iter()
+            .map(|e| (e.binding, e.binding_type.as_str()))
+            .collect();
+        assert_eq!(bindings, configured, "{}", template.name());
+    }
+}
+
+#[test]
+fn test_templates_export() {
+    for template in ProjectTemplate::ALL {
+        let state = template.state();
+        let dir = std::env::temp_dir().join(format!(
+            "wgpu_test_template_{}",
+            template.name().to_lowercase().replace(' ', "_")
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut config = CodeGenConfig::new("template_export".to_string());
+        if state.render_pipeline_panel.is_none() {
+            let shader = state.shader_editor.as_ref().unwrap().source_code.clone();
+            config = config
+                .with_example_type(ExampleType::Compute)
+                .with_shader(shader);
+        }
+        CodeGenerator::new(config.with_playground_state(state))
+            .generate(&dir)
+            .unwrap_or_else(|e| panic!("{}: {}", template.name(), e));
+        assert!(dir.join("src/main.rs").exists(), "{}", template.name());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
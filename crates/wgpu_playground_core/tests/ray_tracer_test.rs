@@ -0,0 +1,102 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::assets::{self, AssetKind};
+use wgpu_playground_core::camera::Camera;
+use wgpu_playground_core::ray_tracer::{
+    RayTracer, TracerMesh, TracerSettings, DEFAULT_MODEL, MAX_BVH_DEPTH,
+};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const WIDTH: u32 = 48;
+const HEIGHT: u32 = 32;
+
+fn default_mesh() -> TracerMesh {
+    let path = assets::resolve(AssetKind::Model, DEFAULT_MODEL).unwrap();
+    TracerMesh::load(&path).expect("the bundled scene loads")
+}
+
+#[test]
+fn test_bundled_scene_builds_a_shallow_bvh() {
+    let mesh = default_mesh();
+    assert!(mesh.triangles.len() > 1000);
+    assert!(mesh.materials.iter().any(|m| m.emission != [0.0; 3]));
+    assert!(mesh.materials.iter().any(|m| m.metallic > 0.5));
+
+    let bvh = wgpu_playground_core::ray_tracer::Bvh::build(&mesh);
+    assert!(bvh.depth() < MAX_BVH_DEPTH, "depth {}", bvh.depth());
+    // Straight down onto the ground in front of the objects
+    let hit = bvh.intersect(&mesh, [0.0, 5.0, 6.0], [0.0, -1.0, 0.0]);
+    assert!(hit.is_some_and(|(t, _)| (t - 5.0).abs() < 1e-4));
+}
+
+#[test]
+fn test_samples_accumulate_until_converged() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, WIDTH, HEIGHT, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let mut tracer = RayTracer::new(&device, wgpu::TextureFormat::Rgba8Unorm, WIDTH, HEIGHT);
+        tracer.set_mesh(&device, default_mesh());
+        tracer.settings = TracerSettings {
+            samples_per_frame: 2,
+            max_samples: 5,
+            ..Default::default()
+        };
+        let camera = Camera::framing([0.0, 1.0, 0.0], 3.5);
+        let aspect = WIDTH as f32 / HEIGHT as f32;
+        tracer.update(camera.view_projection(aspect), camera.eye());
+
+        for expected in [2, 4, 5, 5] {
+            tracer.render(&device, &queue, &view, (WIDTH, HEIGHT));
+            assert_eq!(tracer.sample_count(), expected);
+        }
+        assert!(tracer.is_converged());
+
+        let image = capture_texture(&device, &queue, &target)
+            .await
+            .expect("image captures");
+        // The objects, the ground and the sky
+        let mut colors: Vec<_> = image.pixels().map(|p| p.0).collect();
+        colors.sort_unstable();
+        colors.dedup();
+        assert!(colors.len() > 50, "{} colors", colors.len());
+
+        // Moving the camera starts over
+        let mut moved = camera.clone();
+        moved.orbit.yaw += 0.5;
+        tracer.update(moved.view_projection(aspect), moved.eye());
+        tracer.render(&device, &queue, &view, (WIDTH, HEIGHT));
+        assert_eq!(tracer.sample_count(), 2);
+    });
+}
+
+#[test]
+fn test_bvh_heatmap_without_mesh_shows_no_visits() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, WIDTH, HEIGHT, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let mut tracer = RayTracer::new(&device, wgpu::TextureFormat::Rgba8Unorm, WIDTH, HEIGHT);
+        tracer.settings.show_bvh = true;
+        let camera = Camera::framing([0.0; 3], 1.0);
+        tracer.update(camera.view_projection(1.5), camera.eye());
+        tracer.render(&device, &queue, &view, (WIDTH, HEIGHT));
+
+        let image = capture_texture(&device, &queue, &target)
+            .await
+            .expect("image captures");
+        let first = *image.get_pixel(0, 0);
+        assert!(image.pixels().all(|p| *p == first));
+        // Cold end of the ramp: blue
+        assert!(first.0[2] > first.0[0] && first.0[2] > first.0[1]);
+    });
+}
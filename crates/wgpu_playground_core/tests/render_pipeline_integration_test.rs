@@ -140,10 +140,74 @@ fn test_instanced_rendering_layout() {
 #[test]
 fn test_pipeline_cache_operations() {
     let cache = PipelineCache::new();
+    let shader = ShaderModule::from_source("@vertex fn main() {}", Some("vertex")).unwrap();
+    let key = PipelineKey::new(
+        &RenderPipelineDescriptor::new(Some("test_pipeline")),
+        &shader,
+        None,
+    );
 
     assert!(cache.is_empty());
     assert_eq!(cache.len(), 0);
-    assert!(!cache.contains("test_pipeline"));
+    assert!(!cache.contains(&key));
+}
+
+#[test]
+fn test_pipeline_cache_get_or_create() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let vertex_shader = ShaderModule::from_source(
+            "@vertex fn main() -> @builtin(position) vec4<f32> { return vec4<f32>(0.0); }",
+            Some("vertex"),
+        )
+        .unwrap();
+        let fragment_shader = ShaderModule::from_source(
+            "@fragment fn main() -> @location(0) vec4<f32> { return vec4<f32>(1.0); }",
+            Some("fragment"),
+        )
+        .unwrap();
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("test_layout"),
+            bind_group_layouts: &[],
+            immediate_size: 0,
+        });
+        let opaque = RenderPipelineDescriptor::new(Some("opaque"))
+            .with_fragment_target(ColorTargetState::new(wgpu::TextureFormat::Rgba8Unorm));
+        let blended = RenderPipelineDescriptor::new(Some("blended")).with_fragment_target(
+            ColorTargetState::new(wgpu::TextureFormat::Rgba8Unorm)
+                .with_blend(BlendState::alpha_blending()),
+        );
+
+        let cache = PipelineCache::with_capacity(1);
+        let create = |descriptor: &RenderPipelineDescriptor| {
+            cache
+                .get_or_create(
+                    &device,
+                    &pipeline_layout,
+                    descriptor,
+                    &vertex_shader,
+                    Some(&fragment_shader),
+                )
+                .unwrap()
+        };
+
+        let first = create(&opaque);
+        let second = create(&opaque);
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+        create(&blended);
+        create(&opaque);
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 1);
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 3, 2));
+
+        assert_eq!(cache.invalidate_shader(fragment_shader.source_hash()), 1);
+        assert!(cache.is_empty());
+    });
 }
 
 #[test]
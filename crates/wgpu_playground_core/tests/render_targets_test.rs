@@ -0,0 +1,131 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::render_targets::{
+    read_target, GraphPass, RenderGraph, RenderGraphExecutor, RenderTargetDesc, RenderTargetError,
+    RenderTargetManager,
+};
+
+/// A texel of a 256×256 target covered by all three shapes
+const OVERLAP: (u32, u32) = (128, 140);
+
+fn assert_texel_near(actual: [f32; 4], expected: [f32; 4]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (a - e).abs() < 0.01,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+}
+
+fn create_targets(device: &wgpu::Device, descs: Vec<RenderTargetDesc>) -> RenderTargetManager {
+    let mut targets = RenderTargetManager::new();
+    for desc in descs {
+        targets.create(device, desc).expect("valid target");
+    }
+    targets
+}
+
+#[test]
+fn test_example_graph_resolves_and_samples() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let (descs, graph) = RenderGraph::example();
+        let targets = create_targets(&device, descs);
+        let mut executor = RenderGraphExecutor::new(&device);
+        executor
+            .execute(&device, &queue, &graph, &targets)
+            .expect("example graph executes");
+
+        // The nearest, red shape wins where all three overlap
+        let scene = read_target(&device, &queue, targets.get("scene").unwrap()).unwrap();
+        let (x, y) = OVERLAP;
+        assert_texel_near(scene.texel(x, y).unwrap(), [0.9, 0.2, 0.2, 1.0]);
+        assert_texel_near(scene.texel(2, 2).unwrap(), graph.passes[0].clear_color);
+
+        // The post pass copies the resolved scene into a half-float target
+        let post = read_target(&device, &queue, targets.get("post").unwrap()).unwrap();
+        assert_eq!(post.format, wgpu::TextureFormat::Rgba16Float);
+        assert_texel_near(post.texel(x, y).unwrap(), scene.texel(x, y).unwrap());
+        assert_texel_near(post.texel(2, 2).unwrap(), scene.texel(2, 2).unwrap());
+
+        assert!(matches!(
+            read_target(&device, &queue, targets.get("scene_msaa").unwrap()),
+            Err(RenderTargetError::Readback(_))
+        ));
+    });
+}
+
+#[test]
+fn test_depth_attachment_orders_shapes() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let targets = create_targets(
+            &device,
+            vec![
+                RenderTargetDesc::new("color", wgpu::TextureFormat::Rgba8Unorm, 256, 256),
+                RenderTargetDesc::new("depth", wgpu::TextureFormat::Depth32Float, 256, 256),
+            ],
+        );
+        let mut executor = RenderGraphExecutor::new(&device);
+        let (x, y) = OVERLAP;
+
+        // Without depth the last shape drawn, the blue one, ends up on top
+        let mut graph = RenderGraph {
+            passes: vec![GraphPass {
+                color: Some("color".to_string()),
+                ..GraphPass::new("Shapes")
+            }],
+        };
+        executor.execute(&device, &queue, &graph, &targets).unwrap();
+        let color = read_target(&device, &queue, targets.get("color").unwrap()).unwrap();
+        assert_texel_near(color.texel(x, y).unwrap(), [0.2, 0.4, 0.9, 1.0]);
+
+        graph.passes[0].depth = Some("depth".to_string());
+        executor.execute(&device, &queue, &graph, &targets).unwrap();
+        let color = read_target(&device, &queue, targets.get("color").unwrap()).unwrap();
+        assert_texel_near(color.texel(x, y).unwrap(), [0.9, 0.2, 0.2, 1.0]);
+
+        let depth = read_target(&device, &queue, targets.get("depth").unwrap()).unwrap();
+        assert_eq!(depth.channel_count(), 1);
+        assert_eq!(depth.texel(x, y).unwrap()[0], 0.25);
+        assert_eq!(depth.texel(2, 2).unwrap()[0], 1.0);
+    });
+}
+
+#[test]
+fn test_manager_rejects_and_recreates_targets() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut targets = RenderTargetManager::new();
+        let desc = RenderTargetDesc::new("color", wgpu::TextureFormat::Rgba8Unorm, 32, 16);
+        targets.create(&device, desc.clone()).unwrap();
+        assert_eq!(
+            targets.create(&device, desc.clone()).err(),
+            Some(RenderTargetError::DuplicateName("color".to_string()))
+        );
+
+        targets.recreate(&device);
+        assert_eq!(targets.descs(), vec![desc]);
+        let texture = targets.get("color").unwrap().texture();
+        assert_eq!((texture.width(), texture.height()), (32, 16));
+
+        assert!(targets.remove("color"));
+        assert!(!targets.remove("color"));
+        assert!(targets.is_empty());
+    });
+}
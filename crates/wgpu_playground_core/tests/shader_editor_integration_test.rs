@@ -153,3 +153,22 @@ fn test_shader_editor_reset_clears_error() {
     // Should be valid again
     assert!(editor.validate());
 }
+
+#[test]
+fn test_shader_editor_benchmark_compile() {
+    let Some((device, _queue)) = pollster::block_on(common::create_test_device()) else {
+        eprintln!("Skipping test: could not create wgpu device");
+        return;
+    };
+
+    let mut editor = ShaderEditor::new();
+    let report = editor.run_benchmark(&device).clone().unwrap();
+    assert_eq!(report.iterations, 20);
+    assert_eq!(report.front_end.warm.unwrap().count, 19);
+    assert!(report.pipeline.is_some());
+    assert!(report.pipeline_target.is_some());
+
+    editor.set_source_code("fn broken(".to_string());
+    assert!(editor.run_benchmark(&device).is_err());
+    assert!(editor.benchmark_result().unwrap().is_err());
+}
@@ -95,3 +95,22 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     assert!(shader.source().contains("texture_2d"));
     assert!(shader.source().contains("textureSample"));
 }
+
+#[test]
+fn test_post_processing_shaders_share_an_include() {
+    for filename in [
+        "post_processing_blur.wgsl",
+        "post_processing_edge.wgsl",
+        "post_processing_grayscale.wgsl",
+        "post_processing_passthrough.wgsl",
+    ] {
+        let shader = ShaderModule::from_file(filename, None)
+            .unwrap_or_else(|e| panic!("Failed to load {}: {}", filename, e));
+        let source = shader.source();
+        assert!(!source.contains("#include"), "{} was not expanded", filename);
+        assert!(source.contains("fn vs_main"));
+        assert!(source.contains("fn fs_main"));
+        naga::front::wgsl::parse_str(source)
+            .unwrap_or_else(|e| panic!("{} doesn't parse: {}", filename, e));
+    }
+}
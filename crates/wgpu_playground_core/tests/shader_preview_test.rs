@@ -0,0 +1,102 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::shader_preview::{ShaderPreview, PREVIEW_SIZE};
+use wgpu_playground_core::uniform_tweaker::UniformTweaker;
+use wgpu_playground_core::visual_regression::capture_texture;
+
+/// A fullscreen triangle colored from a uniform buffer in group 1
+const SHADER: &str = r#"
+struct Params {
+    base_color: vec3<f32>,
+    brightness: f32,
+}
+
+@group(1) @binding(0) var<uniform> params: Params;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(params.base_color * params.brightness, 1.0);
+}
+"#;
+
+#[test]
+fn test_tweaked_uniforms_reach_the_shader() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut uniforms = UniformTweaker::from_wgsl(SHADER).unwrap();
+        let mut preview = ShaderPreview::new(&device);
+        preview.build(&device, SHADER, &uniforms);
+        assert_eq!(preview.error(), None);
+
+        let center =
+            |image: &image::RgbaImage| *image.get_pixel(PREVIEW_SIZE / 2, PREVIEW_SIZE / 2);
+        uniforms.set("base_color", &[1.0, 0.0, 0.0]);
+        uniforms.set("brightness", &[1.0]);
+        preview.render(&device, &queue, &uniforms);
+        let red = capture_texture(&device, &queue, preview.texture())
+            .await
+            .unwrap();
+        assert_eq!(center(&red).0, [255, 0, 0, 255]);
+
+        // The next frame picks up the new values without a rebuild
+        uniforms.set("base_color", &[0.0, 1.0, 0.0]);
+        uniforms.set("brightness", &[0.0]);
+        preview.render(&device, &queue, &uniforms);
+        let dark = capture_texture(&device, &queue, preview.texture())
+            .await
+            .unwrap();
+        assert_eq!(center(&dark).0, [0, 0, 0, 255]);
+    });
+}
+
+#[test]
+fn test_preview_reports_unsupported_shaders() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut preview = ShaderPreview::new(&device);
+        let compute = "@compute @workgroup_size(1) fn main() {}";
+        preview.build(&device, compute, &UniformTweaker::new());
+        assert!(preview.error().unwrap().contains("@vertex and a @fragment"));
+
+        let textured = SHADER
+            .replace(
+                "@group(1) @binding(0) var<uniform> params: Params;",
+                "@group(1) @binding(0) var<uniform> params: Params;\n\
+             @group(0) @binding(0) var image: texture_2d<f32>;",
+            )
+            .replace(
+                "return vec4<f32>(params.base_color",
+                "return textureLoad(image, vec2<i32>(0), 0) + vec4<f32>(params.base_color",
+            );
+        let uniforms = UniformTweaker::from_wgsl(&textured).unwrap();
+        preview.build(&device, &textured, &uniforms);
+        assert!(preview.error().unwrap().contains("`image`"));
+
+        // A shader the device rejects; naga accepts the entry point names
+        // but the fragment output does not match the texture format
+        let integer_output = SHADER
+            .replace("-> @location(0) vec4<f32>", "-> @location(0) vec4<u32>")
+            .replace(
+                "return vec4<f32>(params.base_color * params.brightness, 1.0);",
+                "return vec4<u32>(1u);",
+            );
+        let uniforms = UniformTweaker::from_wgsl(&integer_output).unwrap();
+        preview.build(&device, &integer_output, &uniforms);
+        assert!(preview.error().is_some());
+    });
+}
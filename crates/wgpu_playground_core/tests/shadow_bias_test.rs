@@ -0,0 +1,150 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::math_utils::{look_at, mat4_mul, orthographic};
+use wgpu_playground_core::shadow_bias::{
+    ShadowScene, ShadowView, BIAS_PRESETS, GROUND_SIZE, SHADOW_FORMATS, SHADOW_PIPELINE,
+};
+
+const SIZE: u32 = 128;
+
+/// Render the shadow term looking straight down at the ground, so texel
+/// `(x, y)` shows the ground at `(x, z)` scaled from `-8..8` to the texture
+fn render_shadow_term(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    scene: &mut ShadowScene,
+) -> Vec<u8> {
+    let half = GROUND_SIZE / 2.0;
+    let view_proj = mat4_mul(
+        &orthographic(half, half, 1.0, 40.0),
+        &look_at([0.0, 20.0, 0.0], [0.0; 3], [0.0, 0.0, -1.0]),
+    );
+    scene.view = ShadowView::ShadowTerm;
+    scene.update(queue, view_proj);
+
+    let texture = create_target(device, SIZE, SIZE, wgpu::TextureFormat::Rgba8Unorm);
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    scene.render(device, queue, &view, (SIZE, SIZE), wgpu::Color::BLACK);
+
+    let bytes_per_row = SIZE * 4;
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Shadow Test Readback"),
+        size: (bytes_per_row * SIZE) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &staging,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(SIZE),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let slice = staging.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    device
+        .poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        })
+        .unwrap();
+    let pixels = slice.get_mapped_range().to_vec();
+    staging.unmap();
+    pixels
+}
+
+/// Share of lit texels over the ground from `min` to `max` in `(x, z)`
+fn lit_fraction(pixels: &[u8], min: [f32; 2], max: [f32; 2]) -> f32 {
+    let texel = |c: f32| ((c / GROUND_SIZE + 0.5) * SIZE as f32) as u32;
+    let (mut lit, mut total) = (0, 0);
+    for y in texel(min[1])..texel(max[1]) {
+        for x in texel(min[0])..texel(max[0]) {
+            total += 1;
+            if pixels[((y * SIZE + x) * 4) as usize] > 127 {
+                lit += 1;
+            }
+        }
+    }
+    lit as f32 / total as f32
+}
+
+fn preset(name: &str) -> (&'static str, f32, f32, f32) {
+    *BIAS_PRESETS.iter().find(|p| p.0 == name).unwrap()
+}
+
+/// Ground in full light
+const OPEN_GROUND: ([f32; 2], [f32; 2]) = ([3.0, -7.0], [7.0, -4.0]);
+
+/// Ground right behind the thin wall, seen from the light
+const BEHIND_WALL: ([f32; 2], [f32; 2]) = ([1.15, -0.2], [1.4, 0.2]);
+
+#[test]
+fn test_bias_removes_acne() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut scene =
+            ShadowScene::new(&device, &queue, wgpu::TextureFormat::Rgba8Unorm, SIZE, SIZE);
+        let (min, max) = OPEN_GROUND;
+
+        scene.apply_preset(preset("No bias"));
+        let pixels = render_shadow_term(&device, &queue, &mut scene);
+        let acne = lit_fraction(&pixels, min, max);
+        assert!(acne < 0.9, "open ground {} lit without bias", acne);
+
+        for format in SHADOW_FORMATS {
+            scene.shadow_state.format = format;
+            scene.apply_preset(preset("Balanced"));
+            let pixels = render_shadow_term(&device, &queue, &mut scene);
+            assert!(!scene.errors().has_error(SHADOW_PIPELINE));
+            assert_eq!(scene.shadow_map_info(), (1024, format));
+            let lit = lit_fraction(&pixels, min, max);
+            assert!(lit > 0.99, "{:?}: open ground {} lit", format, lit);
+        }
+    });
+}
+
+#[test]
+fn test_too_much_bias_detaches_shadows() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut scene =
+            ShadowScene::new(&device, &queue, wgpu::TextureFormat::Rgba8Unorm, SIZE, SIZE);
+        let (min, max) = BEHIND_WALL;
+
+        scene.apply_preset(preset("Balanced"));
+        let pixels = render_shadow_term(&device, &queue, &mut scene);
+        assert_eq!(lit_fraction(&pixels, min, max), 0.0);
+
+        scene.apply_preset(preset("Too much"));
+        let pixels = render_shadow_term(&device, &queue, &mut scene);
+        assert_eq!(lit_fraction(&pixels, min, max), 1.0);
+
+        // Clamping the bias brings the shadow back, where the device can
+        scene.shadow_state.depth_bias_clamp = 0.001;
+        let pixels = render_shadow_term(&device, &queue, &mut scene);
+        let expected = if scene.errors().has_error(SHADOW_PIPELINE) {
+            // The previous pipeline stays in use
+            1.0
+        } else {
+            0.0
+        };
+        assert_eq!(lit_fraction(&pixels, min, max), expected);
+    });
+}
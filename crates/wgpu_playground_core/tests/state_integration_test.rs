@@ -288,6 +288,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
             source_code: shader_code.to_string(),
             label: "transform_shader".to_string(),
             file_path: "transform.wgsl".to_string(),
+            ..Default::default()
         }),
         ..Default::default()
     };
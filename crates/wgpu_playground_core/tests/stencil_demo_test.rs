@@ -0,0 +1,97 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::render_pipeline::{CompareFunction, StencilOperation};
+use wgpu_playground_core::stencil_demo::{
+    always, example_steps, execute, StencilShape, StencilStep, DEMO_SIZE,
+};
+
+/// Texel of a normalized device coordinate
+fn texel(x: f32, y: f32) -> (u32, u32) {
+    let size = DEMO_SIZE as f32;
+    (
+        ((x + 1.0) * 0.5 * size) as u32,
+        ((1.0 - y) * 0.5 * size) as u32,
+    )
+}
+
+#[test]
+fn test_example_steps_write_and_test_the_stencil() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let snapshots = execute(&device, &queue, &example_steps()).expect("demo runs");
+        assert_eq!(snapshots.len(), 3);
+
+        let circle_only = texel(-0.25, 0.5);
+        let both = texel(-0.25, -0.1);
+        let bar_only = texel(0.5, -0.1);
+        let outside = texel(0.8, 0.8);
+        let stencil = |step: usize, (x, y): (u32, u32)| snapshots[step].stencil_at(x, y).unwrap();
+
+        assert_eq!(stencil(0, circle_only), 1);
+        assert_eq!(stencil(0, both), 1);
+        assert_eq!(stencil(0, bar_only), 0);
+        assert_eq!(stencil(1, both), 2);
+        assert_eq!(stencil(1, bar_only), 1);
+        assert_eq!(stencil(1, outside), 0);
+
+        // The masks never touch the color target
+        let clear = snapshots[1].color_at(outside.0, outside.1).unwrap();
+        assert!(snapshots[1].color.chunks(4).all(|texel| texel == clear));
+
+        // The triangle is drawn only where the stencil holds exactly 1
+        let color = |(x, y): (u32, u32)| snapshots[2].color_at(x, y).unwrap();
+        assert_ne!(color(bar_only), clear);
+        assert_eq!(color(both), clear);
+        assert_eq!(snapshots[2].stencil, snapshots[1].stencil);
+    });
+}
+
+#[test]
+fn test_fail_op_and_write_mask() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut invert_outside = StencilStep::new(
+            "Invert where the test fails",
+            StencilShape::FullScreen,
+            always(StencilOperation::Keep),
+            1,
+        );
+        invert_outside.face.compare = CompareFunction::Equal;
+        invert_outside.face.fail_op = StencilOperation::Invert;
+        invert_outside.write_mask = 0x0F;
+        let steps = vec![
+            StencilStep::new(
+                "Circle",
+                StencilShape::Circle,
+                always(StencilOperation::Replace),
+                1,
+            ),
+            invert_outside.clone(),
+        ];
+        let snapshots = execute(&device, &queue, &steps).unwrap();
+
+        let (x, y) = texel(-0.25, 0.15);
+        assert_eq!(snapshots[1].stencil_at(x, y), Some(1));
+        // Only the low four bits of 0 are inverted
+        let (x, y) = texel(0.9, 0.9);
+        assert_eq!(snapshots[1].stencil_at(x, y), Some(0x0F));
+        assert_eq!(invert_outside.apply(0), 0x0F);
+
+        let histogram = snapshots[1].histogram();
+        assert_eq!(
+            histogram.iter().map(|(v, _)| *v).collect::<Vec<_>>(),
+            vec![1, 0x0F]
+        );
+        let total: usize = histogram.iter().map(|(_, n)| n).sum();
+        assert_eq!(total, (DEMO_SIZE * DEMO_SIZE) as usize);
+    });
+}
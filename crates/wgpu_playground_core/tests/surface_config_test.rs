@@ -0,0 +1,116 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::surface_config::{GammaOverlay, OVERLAY_ROWS};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 60;
+
+/// Render the test card into a cleared texture of `format`, filling its
+/// left half and clipping away the bottom 3 rows
+async fn render_card(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    format: wgpu::TextureFormat,
+) -> image::RgbaImage {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Gamma Card Target"),
+        size: wgpu::Extent3d {
+            width: WIDTH,
+            height: HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let overlay = GammaOverlay::new(device, format);
+    assert_eq!(overlay.format(), format);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let _clear = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::RED),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+    }
+    let card = [0.0, 0.0, (WIDTH / 2) as f32, HEIGHT as f32];
+    let clip = [0.0, 0.0, WIDTH as f32, (HEIGHT - 3) as f32];
+    overlay.render(&mut encoder, &view, [WIDTH, HEIGHT], card, clip);
+    queue.submit(std::iter::once(encoder.finish()));
+
+    capture_texture(device, queue, &texture).await.unwrap()
+}
+
+/// Gray of the shader half of each row, as stored in the texture
+fn row_grays(image: &image::RgbaImage) -> Vec<u8> {
+    let row_height = HEIGHT / OVERLAY_ROWS.len() as u32;
+    (0..OVERLAY_ROWS.len() as u32)
+        .map(|row| {
+            image
+                .get_pixel(WIDTH * 3 / 8, row * row_height + row_height / 2)
+                .0[0]
+        })
+        .collect()
+}
+
+fn assert_near(actual: u8, expected: u8) {
+    assert!(
+        actual.abs_diff(expected) <= 2,
+        "expected about {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_card_on_linear_and_srgb_targets() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        // Stored bytes are what the display shows: 188 is 50% light, 128
+        // is 22% and 223 is 74%
+        let linear = render_card(&device, &queue, wgpu::TextureFormat::Rgba8Unorm).await;
+        let grays = row_grays(&linear);
+        assert_near(grays[0], 188);
+        assert_near(grays[1], 128);
+        assert_near(grays[2], 188);
+
+        let srgb = render_card(&device, &queue, wgpu::TextureFormat::Rgba8UnormSrgb).await;
+        let grays = row_grays(&srgb);
+        assert_near(grays[0], 188);
+        assert_near(grays[1], 188);
+        assert_near(grays[2], 223);
+
+        for image in [&linear, &srgb] {
+            // The checker half is pure black and white
+            let a = image.get_pixel(2, 2).0;
+            let b = image.get_pixel(3, 2).0;
+            assert_eq!(a[0].max(b[0]), 255);
+            assert_eq!(a[0].min(b[0]), 0);
+            // Outside the card and below the clip the target is untouched
+            assert_eq!(image.get_pixel(WIDTH - 1, 0).0, [255, 0, 0, 255]);
+            assert_eq!(image.get_pixel(0, HEIGHT - 1).0, [255, 0, 0, 255]);
+        }
+    });
+}
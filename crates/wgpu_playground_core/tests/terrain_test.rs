@@ -0,0 +1,111 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::buffer_io::read_buffer;
+use wgpu_playground_core::camera::Camera;
+use wgpu_playground_core::terrain::{terrain_height, Terrain, TerrainNoise, HEIGHTMAP_SIZE};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const SIZE: u32 = 64;
+const SKY: wgpu::Color = wgpu::Color {
+    r: 0.0,
+    g: 0.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+fn read_heightmap(device: &wgpu::Device, queue: &wgpu::Queue, terrain: &Terrain) -> Vec<f32> {
+    let bytes_per_row = HEIGHTMAP_SIZE * 4;
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Heightmap Readback"),
+        size: (bytes_per_row * HEIGHTMAP_SIZE) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_texture_to_buffer(
+        terrain.heightmap().as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: None,
+            },
+        },
+        terrain.heightmap().size(),
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+    let bytes = read_buffer(device, queue, &buffer).expect("heightmap reads back");
+    bytemuck::cast_slice(&bytes).to_vec()
+}
+
+#[test]
+fn test_heightmap_matches_cpu_noise() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let mut terrain = Terrain::new(&device, wgpu::TextureFormat::Rgba8Unorm, SIZE, SIZE);
+        let camera = Camera::framing([0.0; 3], 40.0);
+        for noise in [
+            TerrainNoise::default(),
+            TerrainNoise {
+                octaves: 3,
+                seed: 9,
+                ..TerrainNoise::default()
+            },
+        ] {
+            terrain.noise = noise;
+            terrain.update(camera.view_projection(1.0), camera.eye());
+            terrain.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+            let heights = read_heightmap(&device, &queue, &terrain);
+
+            let half = (HEIGHTMAP_SIZE / 2) as i32;
+            for (x, z) in [(0, 0), (-512, -512), (511, 511), (137, -260), (-3, 401)] {
+                let index = ((z + half) as u32 * HEIGHTMAP_SIZE + (x + half) as u32) as usize;
+                let expected = terrain_height(&noise, x as f32, z as f32);
+                assert!(
+                    (heights[index] - expected).abs() < 1e-3 * noise.height,
+                    "{} != {} at {},{}",
+                    heights[index],
+                    expected,
+                    x,
+                    z
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn test_terrain_fills_the_view_from_above() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, wgpu::TextureFormat::Rgba8Unorm);
+        let view = target.create_view(&Default::default());
+        let mut terrain = Terrain::new(&device, wgpu::TextureFormat::Rgba8Unorm, SIZE, SIZE);
+        let mut camera = Camera::framing([0.0; 3], 40.0);
+        camera.orbit.pitch = 1.2;
+        camera.far = 2000.0;
+
+        for wireframe in [false, true] {
+            terrain.wireframe = wireframe;
+            terrain.update(camera.view_projection(1.0), camera.eye());
+            terrain.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+            let image = capture_texture(&device, &queue, &target)
+                .await
+                .expect("terrain captures");
+            let sky = image.pixels().filter(|p| p.0[..3] == [0, 0, 255]).count();
+            assert_eq!(sky, 0, "no gaps in the terrain, wireframe {}", wireframe);
+        }
+    });
+}
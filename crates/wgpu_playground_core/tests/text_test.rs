@@ -0,0 +1,189 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::math_utils::MAT4_IDENTITY;
+use wgpu_playground_core::text::{TextAnchor, TextError, TextRenderer, TextSection};
+use wgpu_playground_core::visual_regression::capture_texture;
+
+const WIDTH: u32 = 128;
+const HEIGHT: u32 = 64;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+fn create_texture(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Text Test Texture"),
+        size: wgpu::Extent3d {
+            width: WIDTH,
+            height: HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    })
+}
+
+/// Draw the prepared text over black in a pass of the test's own,
+/// optionally with a depth attachment cleared to `depth`
+fn draw(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    renderer: &TextRenderer,
+    target: &wgpu::Texture,
+    depth: Option<(&wgpu::Texture, f32)>,
+) {
+    let view = target.create_view(&Default::default());
+    let depth_view =
+        depth.map(|(texture, clear)| (texture.create_view(&Default::default()), clear));
+    let mut encoder = device.create_command_encoder(&Default::default());
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Text Test Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: depth_view.as_ref().map(|(view, clear)| {
+                wgpu::RenderPassDepthStencilAttachment {
+                    view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(*clear),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        renderer.render(&mut render_pass);
+    }
+    queue.submit(std::iter::once(encoder.finish()));
+}
+
+/// Lit pixels in the columns `columns`
+fn lit(image: &image::RgbaImage, columns: std::ops::Range<u32>) -> usize {
+    columns
+        .flat_map(|x| (0..HEIGHT).map(move |y| (x, y)))
+        .filter(|&(x, y)| image.get_pixel(x, y).0[0] > 64)
+        .count()
+}
+
+#[test]
+fn test_text_draws_where_anchored_and_reuses_the_atlas() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_texture(
+            &device,
+            FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        );
+        let mut renderer = TextRenderer::new(&device, FORMAT, None, 1);
+        let sections = [TextSection::new("wgpu", TextAnchor::Screen([4.0, 4.0])).with_size(20.0)];
+        renderer
+            .prepare(&device, &queue, &sections, (WIDTH, HEIGHT), &MAT4_IDENTITY)
+            .expect("text fits");
+        assert_eq!(renderer.glyph_count(), 4);
+        assert_eq!(renderer.atlas_glyphs(), 4);
+        draw(&device, &queue, &renderer, &target, None);
+
+        let image = capture_texture(&device, &queue, &target)
+            .await
+            .expect("text captures");
+        let width = renderer.measure("wgpu", 20.0)[0] as u32;
+        assert!(lit(&image, 4..4 + width) > 20, "the text is drawn");
+        assert_eq!(lit(&image, 8 + width..WIDTH), 0, "nothing right of it");
+
+        // The same glyphs again come from the atlas; a new size rasterizes anew
+        let again = [
+            TextSection::new("gpu", TextAnchor::World([0.0, 0.0, 0.5])).with_size(20.0),
+            TextSection::new("w", TextAnchor::Screen([0.0, 0.0])).with_size(30.0),
+        ];
+        renderer
+            .prepare(&device, &queue, &again, (WIDTH, HEIGHT), &MAT4_IDENTITY)
+            .expect("text fits");
+        assert_eq!(renderer.glyph_count(), 4);
+        assert_eq!(renderer.atlas_glyphs(), 5);
+    });
+}
+
+#[test]
+fn test_scene_labels_respect_depth() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_texture(
+            &device,
+            FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        );
+        let depth = create_texture(
+            &device,
+            DEPTH_FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+        );
+        let mut renderer = TextRenderer::new(&device, FORMAT, Some(DEPTH_FORMAT), 1);
+        // A label in the scene on the right, one on the screen on the left
+        let sections = [
+            TextSection::new("AB", TextAnchor::World([0.5, 0.0, 0.5])).with_size(20.0),
+            TextSection::new("AB", TextAnchor::Screen([2.0, 2.0])).with_size(20.0),
+        ];
+        renderer
+            .prepare(&device, &queue, &sections, (WIDTH, HEIGHT), &MAT4_IDENTITY)
+            .expect("text fits");
+        let half = WIDTH / 2;
+
+        draw(&device, &queue, &renderer, &target, Some((&depth, 1.0)));
+        let open = capture_texture(&device, &queue, &target)
+            .await
+            .expect("text captures");
+        assert!(lit(&open, 0..half) > 0);
+        assert!(lit(&open, half..WIDTH) > 0);
+
+        // Nearer geometry everywhere hides the scene label only
+        draw(&device, &queue, &renderer, &target, Some((&depth, 0.25)));
+        let covered = capture_texture(&device, &queue, &target)
+            .await
+            .expect("text captures");
+        assert!(lit(&covered, 0..half) > 0);
+        assert_eq!(lit(&covered, half..WIDTH), 0);
+    });
+}
+
+#[test]
+fn test_invalid_font_is_rejected() {
+    pollster::block_on(async {
+        let Some((device, _queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut renderer = TextRenderer::new(&device, FORMAT, None, 1);
+        assert_eq!(
+            renderer.set_font(b"not a font".to_vec()),
+            Err(TextError::InvalidFont)
+        );
+        assert!(renderer.measure("still works", 16.0)[0] > 0.0);
+    });
+}
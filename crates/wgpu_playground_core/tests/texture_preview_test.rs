@@ -1,6 +1,8 @@
 mod common;
 
 use common::create_test_device;
+use wgpu_playground_core::asset_loader::DecodedImage;
+use wgpu_playground_core::procedural_texture::ProceduralSettings;
 use wgpu_playground_core::texture_preview::TexturePreviewState;
 
 #[test]
@@ -34,7 +36,13 @@ fn test_texture_preview_procedural_generation() {
         preview.initialize(&device);
 
         // Generate procedural texture
-        preview.generate_procedural_texture(&device, &queue, 256, 256);
+        preview.generate_procedural_texture(
+            &device,
+            &queue,
+            &ProceduralSettings::default(),
+            256,
+            256,
+        );
 
         assert!(preview.has_texture());
     });
@@ -65,6 +73,36 @@ fn test_texture_preview_image_loading() {
     });
 }
 
+#[test]
+fn test_texture_preview_upload_over_frames() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut preview = TexturePreviewState::new();
+        preview.initialize(&device);
+
+        let image = DecodedImage {
+            width: 16,
+            height: 16,
+            rgba: vec![128; 16 * 16 * 4],
+        };
+        preview.begin_image_upload(&device, image);
+        assert!(preview.has_texture());
+
+        // Four rows per frame
+        let budget = 4 * 16 * 4;
+        assert_eq!(preview.continue_upload(&queue, budget), Some(0.25));
+        assert_eq!(preview.continue_upload(&queue, budget), Some(0.5));
+        assert_eq!(preview.continue_upload(&queue, budget), Some(0.75));
+        assert_eq!(preview.continue_upload(&queue, budget), None);
+        assert_eq!(preview.continue_upload(&queue, budget), None);
+        assert!(preview.render(&device, &queue).is_some());
+    });
+}
+
 #[test]
 fn test_texture_preview_render() {
     pollster::block_on(async {
@@ -77,7 +115,13 @@ fn test_texture_preview_render() {
         preview.initialize(&device);
 
         // Generate procedural texture
-        preview.generate_procedural_texture(&device, &queue, 128, 128);
+        preview.generate_procedural_texture(
+            &device,
+            &queue,
+            &ProceduralSettings::default(),
+            128,
+            128,
+        );
 
         // Render preview
         let result = preview.render(&device, &queue);
@@ -100,7 +144,13 @@ fn test_texture_preview_different_sizes() {
         for (width, height) in sizes {
             let mut preview = TexturePreviewState::new();
             preview.initialize(&device);
-            preview.generate_procedural_texture(&device, &queue, width, height);
+            preview.generate_procedural_texture(
+                &device,
+                &queue,
+                &ProceduralSettings::default(),
+                width,
+                height,
+            );
 
             assert!(preview.has_texture());
         }
@@ -1,6 +1,7 @@
 /// Integration test to verify theme switching workflow
 use wgpu_playground_core::settings_panel::SettingsPanel;
 use wgpu_playground_core::state::{PlaygroundState, Theme};
+use wgpu_playground_core::theme::{BackdropPattern, ThemePalette};
 
 #[test]
 fn test_theme_switching_workflow() {
@@ -71,6 +72,8 @@ fn test_theme_independence_from_other_state() {
     let state = PlaygroundState {
         version: "1.0".to_string(),
         theme: Theme::Light,
+        language: None,
+        custom_theme: None,
         buffer_panel: None,
         texture_panel: None,
         sampler_panel: None,
@@ -82,6 +85,7 @@ fn test_theme_independence_from_other_state() {
         api_coverage: None,
         tutorial_state: None,
         learning_progress: None,
+        device_config: None,
     };
 
     state.save_to_file(&state_file).expect("Failed to save");
@@ -96,3 +100,40 @@ fn test_theme_independence_from_other_state() {
     // Clean up
     std::fs::remove_file(&state_file).ok();
 }
+
+#[test]
+fn test_custom_theme_persistence() {
+    let mut settings = SettingsPanel::new();
+    let custom = ThemePalette {
+        accent: [255, 120, 0],
+        preview_clear: [0.0, 0.0, 0.0, 0.0],
+        backdrop: BackdropPattern::Stripes,
+        ..ThemePalette::light()
+    };
+    settings.set_custom_theme(custom.clone());
+    settings.set_theme(Theme::Custom);
+    assert_eq!(settings.palette(), custom);
+
+    let state = PlaygroundState {
+        theme: settings.get_theme(),
+        custom_theme: Some(settings.custom_theme().clone()),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&state).unwrap();
+    let loaded: PlaygroundState = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.theme, Theme::Custom);
+    assert_eq!(loaded.custom_theme, Some(custom.clone()));
+
+    // The custom palette is kept while a built-in theme is selected
+    settings.set_theme(Theme::Dark);
+    assert_eq!(settings.palette(), ThemePalette::dark());
+    assert_eq!(settings.custom_theme(), &custom);
+}
+
+#[test]
+fn test_state_without_custom_theme_loads() {
+    let loaded: PlaygroundState =
+        serde_json::from_str(r#"{"version":"1.0","theme":"Light"}"#).unwrap();
+    assert_eq!(loaded.theme, Theme::Light);
+    assert!(loaded.custom_theme.is_none());
+}
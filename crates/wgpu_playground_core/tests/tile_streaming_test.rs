@@ -0,0 +1,84 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::math_utils::{look_at, mat4_mul, perspective, Mat4};
+use wgpu_playground_core::tile_streaming::{TileId, TileStreamer, MIP_COUNT};
+
+const SIZE: u32 = 256;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// A camera low over the ground, looking towards the horizon
+fn view_proj() -> Mat4 {
+    let view = look_at([0.0, 1.5, 6.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+    mat4_mul(&perspective(60.0_f32.to_radians(), 1.0, 0.1, 200.0), &view)
+}
+
+/// Render and wait for the feedback of every frame
+fn run_frames(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    streamer: &mut TileStreamer,
+    target: &wgpu::TextureView,
+    frames: usize,
+) {
+    for _ in 0..frames {
+        streamer.render(device, queue, target, view_proj(), wgpu::Color::BLACK);
+        assert!(streamer.wait_for_feedback(device));
+    }
+}
+
+#[test]
+fn test_requested_tiles_stream_in() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut streamer = TileStreamer::new(&device, &queue, FORMAT);
+        assert_eq!(streamer.atlas().len(), 1);
+        assert!(streamer.atlas().slot_of(TileId::coarsest()).is_some());
+
+        // The first frame only has the coarsest tile; its feedback asks for more
+        run_frames(&device, &queue, &mut streamer, &target, 1);
+        let stats = streamer.stats();
+        assert_eq!(stats.feedback_frames, 1);
+        assert!(stats.requested > 1, "the view needs finer tiles");
+
+        // Enough frames to upload everything within the budget
+        streamer.set_upload_budget(64);
+        run_frames(&device, &queue, &mut streamer, &target, 8);
+        let stats = streamer.stats();
+        assert_eq!(stats.pending, 0, "every requested tile is resident");
+        assert!(stats.uploaded_total > 0);
+        assert!(streamer.atlas().len() > 1);
+        // Tiles near the camera need the finest mip
+        assert!(streamer.atlas().resident_per_mip()[0] > 0);
+
+        streamer.flush(&queue);
+        assert_eq!(streamer.atlas().len(), 1);
+    });
+}
+
+#[test]
+fn test_mip_bias_requests_coarser_tiles() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut streamer = TileStreamer::new(&device, &queue, FORMAT);
+        streamer.set_mip_bias(MIP_COUNT as f32);
+        run_frames(&device, &queue, &mut streamer, &target, 3);
+
+        let stats = streamer.stats();
+        assert_eq!(stats.requested, 1, "only the coarsest tile is wanted");
+        assert_eq!(stats.uploaded_total, 0);
+        assert_eq!(streamer.atlas().len(), 1);
+    });
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::upload_belt::UploadBelt;
+
+fn read_buffer(device: &wgpu::Device, queue: &wgpu::Queue, buffer: &wgpu::Buffer) -> Vec<u32> {
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Upload Belt Test Readback"),
+        size: buffer.size(),
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+    queue.submit(std::iter::once(encoder.finish()));
+    let slice = staging.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |result| result.expect("buffer maps"));
+    let _ = device.poll(wgpu::PollType::wait_indefinitely());
+    let values = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+    staging.unmap();
+    values
+}
+
+fn target(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Upload Belt Test Target"),
+        size,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    })
+}
+
+#[test]
+fn test_uploads_land_and_chunks_are_reused() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let belt = UploadBelt::new(256);
+        belt.open(&device, &queue);
+        assert!(belt.is_open_for(&queue));
+        let buffer = target(&device, 512);
+
+        for frame in 0..3u32 {
+            // Two writes share a chunk; the third needs another one
+            belt.write_buffer(&queue, &buffer, 0, bytemuck::cast_slice(&[frame; 16]));
+            belt.write_buffer(&queue, &buffer, 64, bytemuck::cast_slice(&[frame + 10; 16]));
+            belt.write_buffer(
+                &queue,
+                &buffer,
+                256,
+                bytemuck::cast_slice(&[frame + 20; 48]),
+            );
+            belt.submit(&queue, std::iter::empty());
+            let _ = device.poll(wgpu::PollType::wait_indefinitely());
+            belt.end_frame();
+
+            let values = read_buffer(&device, &queue, &buffer);
+            assert!(values[..16].iter().all(|&v| v == frame));
+            assert!(values[16..32].iter().all(|&v| v == frame + 10));
+            assert!(values[64..112].iter().all(|&v| v == frame + 20));
+        }
+
+        let stats = belt.stats();
+        assert_eq!(stats.frame_writes, 3);
+        assert_eq!(stats.frame_bytes, 64 + 64 + 192);
+        assert_eq!(stats.total_writes, 9);
+        // Both chunks came back after the first frame and were written again
+        assert_eq!(stats.chunks_allocated, 2);
+        assert_eq!(stats.chunks_reused, 4);
+        assert_eq!(stats.chunks_free, 2);
+        assert_eq!(stats.chunks_in_flight, 0);
+        assert_eq!(stats.fallback_writes, 0);
+    });
+}
+
+#[test]
+fn test_other_queues_write_directly() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let belt = UploadBelt::new(256);
+        assert!(!belt.is_open_for(&queue));
+        let buffer = target(&device, 64);
+        belt.write_buffer(&queue, &buffer, 0, bytemuck::cast_slice(&[7u32; 16]));
+        belt.submit(&queue, std::iter::empty());
+
+        assert!(read_buffer(&device, &queue, &buffer)
+            .iter()
+            .all(|&v| v == 7));
+        let stats = belt.stats();
+        assert_eq!(stats.fallback_writes, 1);
+        assert_eq!(stats.total_writes, 0);
+    });
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::math_utils::{look_at, mat4_mul, perspective, Mat4};
+use wgpu_playground_core::visual_regression::capture_texture;
+use wgpu_playground_core::volumetric_fog::{FogJitter, FogScene, FogView};
+
+const SIZE: u32 = 64;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const SKY: wgpu::Color = wgpu::Color {
+    r: 0.5,
+    g: 0.65,
+    b: 0.85,
+    a: 1.0,
+};
+/// Standing in the fog, looking along the ground
+const EYE: [f32; 3] = [0.0, 1.0, 14.0];
+
+fn view_proj() -> Mat4 {
+    let view = look_at(EYE, [0.0, 1.0, 0.0], [0.0, 1.0, 0.0]);
+    mat4_mul(&perspective(60.0_f32.to_radians(), 1.0, 0.1, 100.0), &view)
+}
+
+/// Mean red channel over a block of pixels
+fn mean(
+    image: &image::RgbaImage,
+    columns: std::ops::Range<u32>,
+    rows: std::ops::Range<u32>,
+) -> f64 {
+    let mut total = 0u64;
+    let mut count = 0u64;
+    for y in rows {
+        for x in columns.clone() {
+            total += u64::from(image.get_pixel(x, y)[0]);
+            count += 1;
+        }
+    }
+    total as f64 / count as f64
+}
+
+/// Render two frames of a still camera
+async fn render_frames(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    fog: &mut FogScene,
+) -> [image::RgbaImage; 2] {
+    let target = create_target(device, SIZE, SIZE, FORMAT);
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut images = Vec::new();
+    for _ in 0..2 {
+        fog.update(queue, view_proj(), EYE, 0.0);
+        fog.render(device, queue, &view, (SIZE, SIZE), SKY);
+        images.push(capture_texture(device, queue, &target).await.unwrap());
+    }
+    images.try_into().unwrap()
+}
+
+#[test]
+fn test_fog_stops_at_the_scene_depth() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut fog = FogScene::new(&device, &queue, FORMAT, SIZE, SIZE);
+        fog.density = 1.0;
+        fog.temporal = false;
+        fog.view = FogView::Transmittance;
+        fog.update(&queue, view_proj(), EYE, 0.0);
+        fog.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+        let transmittance = capture_texture(&device, &queue, &target).await.unwrap();
+
+        // The ground just in front of the camera is seen through little fog,
+        // the horizon through the whole layer
+        let near_ground = mean(&transmittance, 16..48, 60..64);
+        let horizon = mean(&transmittance, 16..48, 30..34);
+        assert!(
+            near_ground > horizon + 20.0,
+            "near ground {near_ground} vs horizon {horizon}"
+        );
+
+        // Without fog the composite is the scene
+        fog.density = 0.0;
+        fog.view = FogView::Composite;
+        fog.update(&queue, view_proj(), EYE, 0.0);
+        fog.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+        let clear = capture_texture(&device, &queue, &target).await.unwrap();
+        fog.view = FogView::Scene;
+        fog.update(&queue, view_proj(), EYE, 0.0);
+        fog.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+        assert_eq!(
+            clear,
+            capture_texture(&device, &queue, &target).await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn test_jitter_moves_between_frames() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut fog = FogScene::new(&device, &queue, FORMAT, SIZE, SIZE);
+        fog.temporal = false;
+        fog.steps = 4;
+        fog.view = FogView::Fog;
+
+        fog.jitter = FogJitter::None;
+        let [first, second] = render_frames(&device, &queue, &mut fog).await;
+        assert_eq!(first, second, "unjittered rays sample the same points");
+
+        fog.jitter = FogJitter::BlueNoise;
+        let [first, second] = render_frames(&device, &queue, &mut fog).await;
+        assert_ne!(first, second);
+        assert_eq!(fog.frame(), 4);
+    });
+}
+
+#[test]
+fn test_temporal_blend_survives_resize() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let mut fog = FogScene::new(&device, &queue, FORMAT, SIZE, SIZE);
+        for size in [SIZE, SIZE / 2] {
+            let target = create_target(&device, size, size, FORMAT);
+            let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+            for time in 0..4 {
+                fog.update(&queue, view_proj(), EYE, time as f32 * 0.1);
+                fog.render(&device, &queue, &view, (size, size), SKY);
+            }
+            let image = capture_texture(&device, &queue, &target).await.unwrap();
+            assert_eq!(image.dimensions(), (size, size));
+        }
+    });
+}
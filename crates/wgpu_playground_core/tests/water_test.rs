@@ -0,0 +1,158 @@
+mod common;
+
+use common::{create_target, create_test_device};
+use wgpu_playground_core::math_utils::{look_at, mat4_mul, perspective, Mat4};
+use wgpu_playground_core::visual_regression::capture_texture;
+use wgpu_playground_core::water::{WaterScene, WaterView};
+
+const SIZE: u32 = 128;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const SKY: wgpu::Color = wgpu::Color {
+    r: 0.4,
+    g: 0.6,
+    b: 0.9,
+    a: 1.0,
+};
+
+fn view_proj(eye: [f32; 3], center: [f32; 3], up: [f32; 3]) -> Mat4 {
+    let view = look_at(eye, center, up);
+    mat4_mul(&perspective(50.0_f32.to_radians(), 1.0, 0.1, 100.0), &view)
+}
+
+/// Mean absolute channel difference over the given rows
+fn difference(a: &image::RgbaImage, b: &image::RgbaImage, rows: std::ops::Range<u32>) -> f64 {
+    let mut total = 0u64;
+    let mut count = 0u64;
+    for y in rows {
+        for x in 0..SIZE {
+            let (pa, pb) = (a.get_pixel(x, y), b.get_pixel(x, y));
+            for c in 0..3 {
+                total += u64::from(pa[c].abs_diff(pb[c]));
+                count += 1;
+            }
+        }
+    }
+    total as f64 / count as f64
+}
+
+/// Render each view of the water from the given camera
+async fn render_views(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    eye: [f32; 3],
+    center: [f32; 3],
+    up: [f32; 3],
+) -> [image::RgbaImage; 3] {
+    let target = create_target(device, SIZE, SIZE, FORMAT);
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut water = WaterScene::new(device, queue, FORMAT, SIZE, SIZE);
+    let mut images = Vec::new();
+    for mode in [
+        WaterView::Combined,
+        WaterView::Reflection,
+        WaterView::Refraction,
+    ] {
+        water.view = mode;
+        water.update(queue, view_proj(eye, center, up), eye, 1.0);
+        water.render(device, queue, &view, (SIZE, SIZE), SKY);
+        images.push(capture_texture(device, queue, &target).await.unwrap());
+    }
+    images.try_into().unwrap()
+}
+
+#[test]
+fn test_looking_down_shows_the_refraction() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let [combined, reflection, refraction] = render_views(
+            &device,
+            &queue,
+            [0.0, 12.0, 0.0],
+            [0.0; 3],
+            [0.0, 0.0, -1.0],
+        )
+        .await;
+        let (to_reflection, to_refraction) = (
+            difference(&combined, &reflection, 0..SIZE),
+            difference(&combined, &refraction, 0..SIZE),
+        );
+        assert!(
+            to_refraction < to_reflection,
+            "the floor shows through ({to_refraction} vs {to_reflection})"
+        );
+    });
+}
+
+#[test]
+fn test_grazing_angles_show_the_reflection() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let [combined, reflection, refraction] = render_views(
+            &device,
+            &queue,
+            [0.0, 0.4, 7.5],
+            [0.0, 0.2, 0.0],
+            [0.0, 1.0, 0.0],
+        )
+        .await;
+        // The far water, just below the horizon
+        let rows = 64..80;
+        let (to_reflection, to_refraction) = (
+            difference(&combined, &reflection, rows.clone()),
+            difference(&combined, &refraction, rows),
+        );
+        assert!(
+            to_reflection < to_refraction,
+            "the pillars and sky are mirrored ({to_reflection} vs {to_refraction})"
+        );
+    });
+}
+
+#[test]
+fn test_waves_animate() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let target = create_target(&device, SIZE, SIZE, FORMAT);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut water = WaterScene::new(&device, &queue, FORMAT, SIZE, SIZE);
+        let eye = [0.0, 4.0, 8.0];
+        let camera = view_proj(eye, [0.0; 3], [0.0, 1.0, 0.0]);
+
+        let render_at = |water: &mut WaterScene, time| {
+            water.update(&queue, camera, eye, time);
+            water.render(&device, &queue, &view, (SIZE, SIZE), SKY);
+        };
+        render_at(&mut water, 0.0);
+        let still = capture_texture(&device, &queue, &target).await.unwrap();
+        render_at(&mut water, 2.0);
+        let moved = capture_texture(&device, &queue, &target).await.unwrap();
+        assert_ne!(still, moved);
+
+        // A flat surface is a still mirror
+        water.wave_strength = 0.0;
+        render_at(&mut water, 0.0);
+        let calm = capture_texture(&device, &queue, &target).await.unwrap();
+        render_at(&mut water, 2.0);
+        assert_eq!(
+            calm,
+            capture_texture(&device, &queue, &target).await.unwrap()
+        );
+
+        // Resizing the offscreen targets keeps the frame intact
+        water.half_resolution = false;
+        render_at(&mut water, 0.0);
+        assert_eq!(water.offscreen_size(), (SIZE, SIZE));
+    });
+}
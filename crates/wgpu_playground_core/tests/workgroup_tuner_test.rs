@@ -0,0 +1,84 @@
+mod common;
+
+use common::create_test_device;
+use wgpu_playground_core::workgroup_tuner::{
+    timing_source, tune, SizeMethod, TunerConfig, TunerError, DEFAULT_TUNER_SHADER,
+};
+
+fn small_config() -> TunerConfig {
+    TunerConfig {
+        invocations: [4096, 1, 1],
+        iterations: 2,
+        storage_size: 4096 * 16,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_tune_default_shader_with_overrides() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let report =
+            tune(&device, &queue, DEFAULT_TUNER_SHADER, &small_config()).expect("tuning succeeds");
+        assert!(matches!(
+            report.analysis.method,
+            SizeMethod::Overrides { .. }
+        ));
+        assert_eq!(report.timing_source, timing_source(&device));
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert!(!report.results.is_empty());
+        for result in &report.results {
+            assert_eq!(result.workgroups[0] * result.size[0], 4096);
+            assert_eq!(result.timing.count, 2);
+            assert!(result.throughput > 0.0);
+        }
+        assert!(report.recommendation().is_some());
+    });
+}
+
+#[test]
+fn test_tune_rewrites_literal_sizes() {
+    pollster::block_on(async {
+        let Some((device, queue)) = create_test_device().await else {
+            eprintln!("Skipping test: No GPU adapter available");
+            return;
+        };
+
+        let source = r#"
+struct Params { scale: f32 }
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> image: array<f32>;
+
+@compute @workgroup_size(8, 8)
+fn scale(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.y * 64u + id.x;
+    if (i < arrayLength(&image)) {
+        image[i] = image[i] * params.scale;
+    }
+}
+"#;
+        let config = TunerConfig {
+            entry_point: "scale".to_string(),
+            invocations: [64, 64, 1],
+            ..small_config()
+        };
+        let report = tune(&device, &queue, source, &config).expect("tuning succeeds");
+        assert_eq!(report.analysis.method, SizeMethod::Codegen);
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert!(report.results.iter().any(|r| r.size == [8, 8, 1]));
+        assert!(report.results.iter().any(|r| r.size[1] == 1));
+
+        let missing = TunerConfig {
+            entry_point: "missing".to_string(),
+            ..config
+        };
+        assert_eq!(
+            tune(&device, &queue, source, &missing).unwrap_err(),
+            TunerError::NoEntryPoint("missing".to_string())
+        );
+    });
+}
@@ -7,12 +7,13 @@
 /// - Multiple bind groups per pipeline
 /// - Uniform buffers with camera, light, and shadow data
 /// - Directional and point lights
-/// - Basic Phong lighting model
+/// - Basic Phong lighting model, imported from a shared shader module
 ///
 /// Run with: cargo run --package wgpu_playground_examples --example lighting_shadows
 use glam::{Vec3, Vec4};
 use wgpu::util::DeviceExt;
 use wgpu_playground_core::shader::ShaderModule;
+use wgpu_playground_core::shader_composer::ShaderComposer;
 
 /// Vertex structure for 3D geometry with normals
 #[repr(C)]
@@ -386,10 +387,15 @@ fn main() {
 
     let main_shader = ShaderModule::from_file("lighting_shadows_main.wgsl", Some("main_shader"))
         .expect("Failed to load main shader");
+    // The main shader imports the shared lighting module
+    let main_shader_source = ShaderComposer::with_library()
+        .compose(main_shader.source())
+        .expect("Failed to compose main shader")
+        .source;
 
     let main_shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("main_shader"),
-        source: wgpu::ShaderSource::Wgsl(main_shader.source().into()),
+        source: wgpu::ShaderSource::Wgsl(main_shader_source.into()),
     });
     println!("✓ Main shader loaded");
 
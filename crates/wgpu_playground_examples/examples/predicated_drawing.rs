@@ -0,0 +1,626 @@
+/// Predicated Drawing Example
+///
+/// WebGPU has no native predication (conditional rendering): there is no way
+/// to make the GPU skip a draw based on a value it computed itself. This
+/// example emulates it with indirect draws:
+/// - Every object gets its own DrawIndirect arguments in one buffer
+/// - A compute pass evaluates the predicate (here: does the object overlap
+///   the view rectangle?) and patches each object's instance_count to 1 or 0
+/// - The render pass records a draw_indirect() for every object; draws whose
+///   instance_count was zeroed do nothing, and the CPU never reads the result
+///
+/// The object index is passed through first_vertex rather than
+/// first_instance, because a non-zero first_instance in indirect draws needs
+/// the optional "indirect-first-instance" feature.
+///
+/// Key WebGPU APIs demonstrated:
+/// - wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT on one buffer
+/// - ComputePass writing draw arguments
+/// - RenderPass::draw_indirect() with per-object offsets
+/// - CommandEncoder::clear_buffer() to reset a counter every frame
+///
+/// Run with: cargo run --package wgpu_playground_examples --example predicated_drawing
+use wgpu::util::DeviceExt;
+
+/// Objects per side of the grid
+const GRID_SIZE: u32 = 8;
+
+/// Total number of objects, each with its own draw
+const OBJECT_COUNT: u32 = GRID_SIZE * GRID_SIZE;
+
+/// Workgroup size used in the compute shader
+/// This must match the @workgroup_size annotation in the compute shader
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Vertices of one object: a quad made of two triangles
+const VERTICES_PER_OBJECT: u32 = 6;
+
+/// Size of DrawIndirect arguments: vertex_count, instance_count,
+/// first_vertex, first_instance
+const DRAW_ARGS_SIZE: u64 = 16;
+
+const TEXTURE_SIZE: u32 = 256;
+
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+const PATCH_SHADER: &str = r#"
+struct Object {
+    center: vec2<f32>,
+    radius: f32,
+    color_index: u32,
+}
+
+struct View {
+    min: vec2<f32>,
+    max: vec2<f32>,
+}
+
+struct DrawArgs {
+    vertex_count: u32,
+    instance_count: u32,
+    first_vertex: u32,
+    first_instance: u32,
+}
+
+@group(0) @binding(0) var<storage, read> objects: array<Object>;
+@group(0) @binding(1) var<uniform> view: View;
+@group(0) @binding(2) var<storage, read_write> draws: array<DrawArgs>;
+@group(0) @binding(3) var<storage, read_write> visible_count: atomic<u32>;
+
+@compute @workgroup_size(64)
+fn patch_draws(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= arrayLength(&objects) {
+        return;
+    }
+
+    // The predicate: does the object's bounding circle overlap the view?
+    let object = objects[index];
+    let closest = clamp(object.center, view.min, view.max);
+    let visible = distance(closest, object.center) <= object.radius;
+
+    // The draw is always recorded; a zero instance count turns it into a no-op
+    draws[index] = DrawArgs(6u, select(0u, 1u, visible), index * 6u, 0u);
+    if visible {
+        atomicAdd(&visible_count, 1u);
+    }
+}
+"#;
+
+const RENDER_SHADER: &str = r#"
+struct Object {
+    center: vec2<f32>,
+    radius: f32,
+    color_index: u32,
+}
+
+struct View {
+    min: vec2<f32>,
+    max: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+}
+
+@group(0) @binding(0) var<storage, read> objects: array<Object>;
+@group(0) @binding(1) var<uniform> view: View;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // first_vertex selects the object, the remainder the quad corner
+    let object = objects[vertex_index / 6u];
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    var palette = array<vec3<f32>, 6>(
+        vec3<f32>(0.9, 0.3, 0.3),
+        vec3<f32>(0.3, 0.9, 0.3),
+        vec3<f32>(0.3, 0.3, 0.9),
+        vec3<f32>(0.9, 0.9, 0.3),
+        vec3<f32>(0.9, 0.3, 0.9),
+        vec3<f32>(0.3, 0.9, 0.9),
+    );
+
+    let world = object.center + corners[vertex_index % 6u] * object.radius;
+    // Map the view rectangle to clip space
+    let clip = (world - view.min) / (view.max - view.min) * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(clip, 0.0, 1.0);
+    out.color = palette[object.color_index % 6u];
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+"#;
+
+/// Object structure shared by the compute and render shaders
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Object {
+    center: [f32; 2],
+    radius: f32,
+    color_index: u32,
+}
+
+// Safety: Object is repr(C) with simple primitive types
+unsafe impl bytemuck::Pod for Object {}
+unsafe impl bytemuck::Zeroable for Object {}
+
+/// Visible rectangle in world space
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct View {
+    min: [f32; 2],
+    max: [f32; 2],
+}
+
+// Safety: View is repr(C) with simple primitive types
+unsafe impl bytemuck::Pod for View {}
+unsafe impl bytemuck::Zeroable for View {}
+
+/// Objects on a grid spanning -4..4 in both directions
+fn create_objects() -> Vec<Object> {
+    (0..OBJECT_COUNT)
+        .map(|i| {
+            let (x, y) = (i % GRID_SIZE, i / GRID_SIZE);
+            Object {
+                center: [x as f32 - 3.5, y as f32 - 3.5],
+                radius: 0.35,
+                color_index: i % 6,
+            }
+        })
+        .collect()
+}
+
+/// A 3x3 window that pans across the grid
+fn view_for_frame(frame: u32) -> View {
+    let offset = frame as f32 * 1.5 - 4.5;
+    View {
+        min: [offset, -1.5],
+        max: [offset + 3.0, 1.5],
+    }
+}
+
+/// CPU reference of the predicate evaluated in the compute shader
+fn is_visible(object: &Object, view: &View) -> bool {
+    let closest = [
+        object.center[0].clamp(view.min[0], view.max[0]),
+        object.center[1].clamp(view.min[1], view.max[1]),
+    ];
+    let dx = closest[0] - object.center[0];
+    let dy = closest[1] - object.center[1];
+    (dx * dx + dy * dy).sqrt() <= object.radius
+}
+
+/// What the GPU decided for one frame, read back for verification only
+struct FrameResult {
+    /// Patched instance_count of every object's draw
+    instance_counts: Vec<u32>,
+    /// Number of visible objects counted by the compute pass
+    visible_count: u32,
+}
+
+/// GPU resources of the predicated scene
+struct PredicatedScene {
+    patch_pipeline: wgpu::ComputePipeline,
+    render_pipeline: wgpu::RenderPipeline,
+    view_buffer: wgpu::Buffer,
+    draw_buffer: wgpu::Buffer,
+    visible_count_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    patch_bind_group: wgpu::BindGroup,
+    render_bind_group: wgpu::BindGroup,
+    texture_view: wgpu::TextureView,
+}
+
+impl PredicatedScene {
+    fn new(device: &wgpu::Device, objects: &[Object]) -> Self {
+        let object_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Object Buffer"),
+            contents: bytemuck::cast_slice(objects),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let view_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("View Buffer"),
+            size: std::mem::size_of::<View>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Written by the compute pass, consumed by draw_indirect
+        let draw_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Predicated Draw Buffer"),
+            size: OBJECT_COUNT as u64 * DRAW_ARGS_SIZE,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let visible_count_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Visible Count Buffer"),
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback Buffer"),
+            size: draw_buffer.size() + visible_count_buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let patch_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Patch Draws Shader"),
+            source: wgpu::ShaderSource::Wgsl(PATCH_SHADER.into()),
+        });
+        let patch_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Patch Draws Pipeline"),
+            layout: None,
+            module: &patch_module,
+            entry_point: Some("patch_draws"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let patch_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Patch Draws Bind Group"),
+            layout: &patch_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: object_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: view_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: draw_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: visible_count_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Predicated Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Predicated Render Pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &render_module,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &render_module,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: TEXTURE_FORMAT,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview_mask: None,
+            cache: None,
+        });
+        let render_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Predicated Render Bind Group"),
+            layout: &render_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: object_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: view_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Predicated Render Texture"),
+            size: wgpu::Extent3d {
+                width: TEXTURE_SIZE,
+                height: TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            patch_pipeline,
+            render_pipeline,
+            view_buffer,
+            draw_buffer,
+            visible_count_buffer,
+            readback_buffer,
+            patch_bind_group,
+            render_bind_group,
+            texture_view,
+        }
+    }
+
+    /// Patch the draws for `view`, render every object through its
+    /// predicated draw and read the patched arguments back
+    fn render_frame(&self, device: &wgpu::Device, queue: &wgpu::Queue, view: &View) -> FrameResult {
+        queue.write_buffer(&self.view_buffer, 0, bytemuck::bytes_of(view));
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Predicated Frame Encoder"),
+        });
+        encoder.clear_buffer(&self.visible_count_buffer, 0, None);
+
+        // 1. Evaluate the predicate and patch the indirect arguments
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Patch Draws Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.patch_pipeline);
+            compute_pass.set_bind_group(0, &self.patch_bind_group, &[]);
+            compute_pass.dispatch_workgroups(OBJECT_COUNT.div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+
+        // 2. Record one draw per object; the CPU does not know which are skipped
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Predicated Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.texture_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.05,
+                            g: 0.05,
+                            b: 0.1,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+            for object in 0..OBJECT_COUNT as u64 {
+                render_pass.draw_indirect(&self.draw_buffer, object * DRAW_ARGS_SIZE);
+            }
+        }
+
+        // 3. Copy the patched arguments out, only to show what happened
+        encoder.copy_buffer_to_buffer(
+            &self.draw_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            self.draw_buffer.size(),
+        );
+        encoder.copy_buffer_to_buffer(
+            &self.visible_count_buffer,
+            0,
+            &self.readback_buffer,
+            self.draw_buffer.size(),
+            self.visible_count_buffer.size(),
+        );
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = self.readback_buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        let _ = device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        });
+        rx.recv()
+            .expect("map callback was dropped")
+            .expect("Failed to map readback buffer");
+
+        let result = {
+            let data = slice.get_mapped_range();
+            let words: &[u32] = bytemuck::cast_slice(&data);
+            let draw_words = (OBJECT_COUNT as usize) * 4;
+            FrameResult {
+                instance_counts: words[..draw_words].chunks(4).map(|args| args[1]).collect(),
+                visible_count: words[draw_words],
+            }
+        };
+        self.readback_buffer.unmap();
+        result
+    }
+}
+
+/// Create GPU device and queue
+async fn create_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::all(),
+        ..wgpu::InstanceDescriptor::new_without_display_handle()
+    });
+
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        })
+        .await
+        .ok()?;
+
+    println!("Using adapter: {}", adapter.get_info().name);
+    println!("Backend: {:?}\n", adapter.get_info().backend);
+
+    adapter
+        .request_device(&wgpu::DeviceDescriptor {
+            required_features: wgpu::Features::empty(),
+            required_limits: wgpu::Limits::default(),
+            label: Some("Predicated Drawing Device"),
+            memory_hints: Default::default(),
+            experimental_features: Default::default(),
+            trace: Default::default(),
+        })
+        .await
+        .ok()
+}
+
+fn main() {
+    env_logger::init();
+
+    println!("=== Predicated Drawing Example ===\n");
+    println!("WebGPU has no predicated draws, so a compute pass zeroes the");
+    println!("instance_count of indirect draws whose predicate fails.\n");
+
+    let device_queue = pollster::block_on(create_device());
+    if device_queue.is_none() {
+        eprintln!("Failed to create GPU device");
+        return;
+    }
+
+    let (device, queue) = device_queue.unwrap();
+    println!("✓ GPU device created\n");
+
+    let objects = create_objects();
+    let scene = PredicatedScene::new(&device, &objects);
+    println!(
+        "Scene: {} objects, {} draw_indirect calls recorded per frame\n",
+        OBJECT_COUNT, OBJECT_COUNT
+    );
+
+    for frame in 0..5 {
+        let view = view_for_frame(frame);
+        let result = scene.render_frame(&device, &queue, &view);
+
+        let expected = objects.iter().filter(|o| is_visible(o, &view)).count() as u32;
+        let matches_cpu = objects
+            .iter()
+            .zip(&result.instance_counts)
+            .all(|(object, &count)| (count == 1) == is_visible(object, &view));
+
+        println!(
+            "Frame {}: view x {:.1}..{:.1}",
+            frame, view.min[0], view.max[0]
+        );
+        println!(
+            "  ✓ {} draws executed, {} skipped by the GPU",
+            result.visible_count,
+            OBJECT_COUNT - result.visible_count
+        );
+        if matches_cpu && result.visible_count == expected {
+            println!("  ✓ Patched draws match the CPU reference\n");
+        } else {
+            println!("  ✗ Patched draws differ from the CPU reference\n");
+        }
+    }
+
+    println!("=== Example Complete ===\n");
+    println!("Key Concepts:");
+    println!("  • Indirect arguments live in a STORAGE | INDIRECT buffer");
+    println!("  • A compute pass writes instance_count = 0 to skip a draw");
+    println!("  • The CPU records every draw and never waits for the predicate");
+    println!("  • first_vertex carries the object index; non-zero first_instance");
+    println!("    in indirect draws needs the indirect-first-instance feature");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_struct_sizes_match_wgsl() {
+        assert_eq!(std::mem::size_of::<Object>(), 16);
+        assert_eq!(std::mem::size_of::<View>(), 16);
+    }
+
+    #[test]
+    fn test_predicate_reference() {
+        let view = View {
+            min: [0.0, 0.0],
+            max: [1.0, 1.0],
+        };
+        let object = |x, y| Object {
+            center: [x, y],
+            radius: 0.5,
+            color_index: 0,
+        };
+        assert!(is_visible(&object(0.5, 0.5), &view));
+        // Outside, but the bounding circle reaches into the view
+        assert!(is_visible(&object(1.4, 0.5), &view));
+        assert!(!is_visible(&object(1.6, 0.5), &view));
+        // Diagonal distance to the corner is about 0.57
+        assert!(!is_visible(&object(1.4, 1.4), &view));
+    }
+
+    #[test]
+    fn test_view_pans_across_grid() {
+        let objects = create_objects();
+        assert_eq!(objects.len(), OBJECT_COUNT as usize);
+        for frame in 0..5 {
+            let view = view_for_frame(frame);
+            let visible = objects.iter().filter(|o| is_visible(o, &view)).count();
+            assert!(visible > 0 && visible < objects.len());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gpu_predicate_matches_cpu() {
+        let Some((device, queue)) = create_device().await else {
+            println!("No GPU available (expected in CI)");
+            return;
+        };
+
+        let objects = create_objects();
+        let scene = PredicatedScene::new(&device, &objects);
+        for frame in 0..5 {
+            let view = view_for_frame(frame);
+            let result = scene.render_frame(&device, &queue, &view);
+            let expected: Vec<u32> = objects
+                .iter()
+                .map(|o| u32::from(is_visible(o, &view)))
+                .collect();
+            assert_eq!(result.instance_counts, expected);
+            assert_eq!(
+                result.visible_count,
+                expected.iter().sum::<u32>(),
+                "frame {}",
+                frame
+            );
+        }
+    }
+}
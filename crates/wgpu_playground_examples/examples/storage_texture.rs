@@ -0,0 +1,602 @@
+/// Storage Texture Example
+///
+/// Compute shaders write images through storage textures instead of render
+/// attachments. This example writes a procedural pattern with both kinds of
+/// storage access:
+/// - Write-only: one dispatch stores a pattern into an `rgba8unorm` texture
+///   with `texture_storage_2d<rgba8unorm, write>`; the shader can only call
+///   textureStore()
+/// - Read-write: a brush orbits across an `r32float` texture for several
+///   frames, and each dispatch loads every texel, fades it and stores it back
+///   in place with `texture_storage_2d<r32float, read_write>`, leaving a
+///   trail. Without read-write access the same effect needs two textures
+///   swapped every frame (ping-pong)
+///
+/// Read-write access is only guaranteed for the single-channel 32-bit
+/// formats (r32float, r32uint, r32sint), and downlevel adapters may not
+/// support it at all, so the example checks the adapter's format features
+/// and skips the second part if it is missing.
+///
+/// Key WebGPU APIs demonstrated:
+/// - wgpu::TextureUsages::STORAGE_BINDING
+/// - wgpu::BindingType::StorageTexture with WriteOnly and ReadWrite access
+/// - Adapter::get_texture_format_features() to detect read-write support
+/// - CommandEncoder::copy_texture_to_buffer() to read the results back
+///
+/// Run with: cargo run --package wgpu_playground_examples --example storage_texture
+use wgpu_playground_core::shader::ShaderModule;
+
+/// Width and height of both storage textures
+const TEXTURE_SIZE: u32 = 256;
+
+/// Workgroup size in each dimension
+/// This must match the @workgroup_size annotation in both compute shaders
+const WORKGROUP_SIZE: u32 = 8;
+
+/// Frames the brush paints into the read-write texture
+const TRAIL_FRAMES: u32 = 12;
+
+/// Fraction of its value a texel keeps from one frame to the next
+const TRAIL_DECAY: f32 = 0.8;
+
+/// Radius of the brush, in texture coordinates
+const BRUSH_RADIUS: f32 = 0.12;
+
+const PATTERN_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+const TRAIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+
+const WRITE_ONLY_SHADER: &str = r#"
+@group(0) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8)
+fn write_pattern(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+
+    // Texel center in 0..1
+    let p = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    let rings = 0.5 + 0.5 * cos(distance(p, vec2<f32>(0.5)) * 40.0);
+    let cell = vec2<u32>(p * 8.0);
+    let checker = f32((cell.x + cell.y) % 2u);
+
+    // Write-only access: the previous contents can't be read
+    textureStore(output, vec2<i32>(id.xy), vec4<f32>(rings, p.x, 0.25 + 0.75 * checker, 1.0));
+}
+"#;
+
+const READ_WRITE_SHADER: &str = r#"
+struct Brush {
+    center: vec2<f32>,
+    radius: f32,
+    decay: f32,
+}
+
+@group(0) @binding(0) var trail: texture_storage_2d<r32float, read_write>;
+@group(0) @binding(1) var<uniform> brush: Brush;
+
+@compute @workgroup_size(8, 8)
+fn paint_trail(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(trail);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+
+    let p = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    let paint = clamp(1.0 - distance(p, brush.center) / brush.radius, 0.0, 1.0);
+
+    // Read-write access: load this texel's value from the previous frame
+    // and store the new one in place. Each invocation only touches its own
+    // texel, so no other invocation races with it
+    let previous = textureLoad(trail, vec2<i32>(id.xy)).r;
+    textureStore(trail, vec2<i32>(id.xy), vec4<f32>(max(previous * brush.decay, paint), 0.0, 0.0, 0.0));
+}
+"#;
+
+/// Brush uniform of the read-write shader
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Brush {
+    center: [f32; 2],
+    radius: f32,
+    decay: f32,
+}
+
+// Safety: Brush is repr(C) with simple primitive types
+unsafe impl bytemuck::Pod for Brush {}
+unsafe impl bytemuck::Zeroable for Brush {}
+
+/// Center of the texel at `(x, y)` in 0..1
+fn texel_center(x: u32, y: u32) -> [f32; 2] {
+    [
+        (x as f32 + 0.5) / TEXTURE_SIZE as f32,
+        (y as f32 + 0.5) / TEXTURE_SIZE as f32,
+    ]
+}
+
+/// CPU reference of the write-only shader's pattern, as RGBA in 0..1
+fn pattern(x: u32, y: u32) -> [f32; 4] {
+    let [px, py] = texel_center(x, y);
+    let distance = ((px - 0.5).powi(2) + (py - 0.5).powi(2)).sqrt();
+    let rings = 0.5 + 0.5 * (distance * 40.0).cos();
+    let checker = (((px * 8.0) as u32 + (py * 8.0) as u32) % 2) as f32;
+    [rings, px, 0.25 + 0.75 * checker, 1.0]
+}
+
+/// The brush circling the center of the texture
+fn brush_for_frame(frame: u32) -> Brush {
+    let angle = frame as f32 * 0.5;
+    Brush {
+        center: [0.5 + 0.35 * angle.cos(), 0.5 + 0.35 * angle.sin()],
+        radius: BRUSH_RADIUS,
+        decay: TRAIL_DECAY,
+    }
+}
+
+/// CPU reference of the read-write shader after `frames` frames
+fn trail_reference(frames: u32) -> Vec<f32> {
+    let mut trail = vec![0.0f32; (TEXTURE_SIZE * TEXTURE_SIZE) as usize];
+    for frame in 0..frames {
+        let brush = brush_for_frame(frame);
+        for y in 0..TEXTURE_SIZE {
+            for x in 0..TEXTURE_SIZE {
+                let [px, py] = texel_center(x, y);
+                let distance =
+                    ((px - brush.center[0]).powi(2) + (py - brush.center[1]).powi(2)).sqrt();
+                let paint = (1.0 - distance / brush.radius).clamp(0.0, 1.0);
+                let texel = &mut trail[(y * TEXTURE_SIZE + x) as usize];
+                *texel = (*texel * brush.decay).max(paint);
+            }
+        }
+    }
+    trail
+}
+
+/// Whether the adapter allows read-write storage access to `format`
+fn supports_read_write(adapter: &wgpu::Adapter, format: wgpu::TextureFormat) -> bool {
+    adapter
+        .get_texture_format_features(format)
+        .flags
+        .contains(wgpu::TextureFormatFeatureFlags::STORAGE_READ_WRITE)
+}
+
+fn create_storage_texture(
+    device: &wgpu::Device,
+    label: &str,
+    format: wgpu::TextureFormat,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        // STORAGE_BINDING lets compute shaders bind it as a storage texture
+        usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+/// Explicit layout of a storage texture binding with the given access
+fn storage_texture_entry(
+    access: wgpu::StorageTextureAccess,
+    format: wgpu::TextureFormat,
+) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::StorageTexture {
+            access,
+            format,
+            view_dimension: wgpu::TextureViewDimension::D2,
+        },
+        count: None,
+    }
+}
+
+fn create_compute_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    source: &str,
+    entry_point: &str,
+    layout: &wgpu::BindGroupLayout,
+) -> wgpu::ComputePipeline {
+    let module = ShaderModule::from_source(source, Some(label))
+        .unwrap()
+        .create_module(device);
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some(label),
+        bind_group_layouts: &[Some(layout)],
+        immediate_size: 0,
+    });
+    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some(label),
+        layout: Some(&pipeline_layout),
+        module: &module,
+        entry_point: Some(entry_point),
+        compilation_options: Default::default(),
+        cache: None,
+    })
+}
+
+fn dispatch(
+    encoder: &mut wgpu::CommandEncoder,
+    label: &str,
+    pipeline: &wgpu::ComputePipeline,
+    bind_group: &wgpu::BindGroup,
+) {
+    let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some(label),
+        timestamp_writes: None,
+    });
+    compute_pass.set_pipeline(pipeline);
+    compute_pass.set_bind_group(0, bind_group, &[]);
+    let workgroups = TEXTURE_SIZE.div_ceil(WORKGROUP_SIZE);
+    compute_pass.dispatch_workgroups(workgroups, workgroups, 1);
+}
+
+/// Copy a texture with 4-byte texels into a buffer and return its bytes
+fn read_texture(device: &wgpu::Device, queue: &wgpu::Queue, texture: &wgpu::Texture) -> Vec<u8> {
+    // 256 texels of 4 bytes already satisfy COPY_BYTES_PER_ROW_ALIGNMENT
+    let bytes_per_row = TEXTURE_SIZE * 4;
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Storage Texture Readback"),
+        size: (bytes_per_row * TEXTURE_SIZE) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Readback Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(TEXTURE_SIZE),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    let _ = device.poll(wgpu::PollType::Wait {
+        submission_index: None,
+        timeout: None,
+    });
+    rx.recv()
+        .expect("map callback was dropped")
+        .expect("Failed to map readback buffer");
+
+    let data = slice.get_mapped_range().to_vec();
+    buffer.unmap();
+    data
+}
+
+/// Run the write-only pass and return the RGBA8 texels
+fn write_pattern(device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<u8> {
+    let texture = create_storage_texture(device, "Pattern Storage Texture", PATTERN_FORMAT);
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Write-Only Bind Group Layout"),
+        entries: &[storage_texture_entry(
+            wgpu::StorageTextureAccess::WriteOnly,
+            PATTERN_FORMAT,
+        )],
+    });
+    let pipeline = create_compute_pipeline(
+        device,
+        "Write-Only Pattern",
+        WRITE_ONLY_SHADER,
+        "write_pattern",
+        &layout,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Write-Only Bind Group"),
+        layout: &layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(&view),
+        }],
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Write-Only Encoder"),
+    });
+    dispatch(&mut encoder, "Write-Only Pass", &pipeline, &bind_group);
+    queue.submit(std::iter::once(encoder.finish()));
+
+    read_texture(device, queue, &texture)
+}
+
+/// Paint `frames` frames of the trail in place and return the texel values
+fn paint_trail(device: &wgpu::Device, queue: &wgpu::Queue, frames: u32) -> Vec<f32> {
+    let texture = create_storage_texture(device, "Trail Storage Texture", TRAIL_FORMAT);
+    let brush_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Brush Buffer"),
+        size: std::mem::size_of::<Brush>() as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Read-Write Bind Group Layout"),
+        entries: &[
+            storage_texture_entry(wgpu::StorageTextureAccess::ReadWrite, TRAIL_FORMAT),
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+    let pipeline = create_compute_pipeline(
+        device,
+        "Read-Write Trail",
+        READ_WRITE_SHADER,
+        "paint_trail",
+        &layout,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Read-Write Bind Group"),
+        layout: &layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: brush_buffer.as_entire_binding(),
+            },
+        ],
+    });
+
+    // One submission per frame so each frame's brush position is used
+    for frame in 0..frames {
+        queue.write_buffer(
+            &brush_buffer,
+            0,
+            bytemuck::bytes_of(&brush_for_frame(frame)),
+        );
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Read-Write Encoder"),
+        });
+        dispatch(&mut encoder, "Read-Write Pass", &pipeline, &bind_group);
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    bytemuck::cast_slice(&read_texture(device, queue, &texture)).to_vec()
+}
+
+/// Largest difference between the GPU pattern and the CPU reference, in 0..255 units
+fn pattern_error(texels: &[u8]) -> u8 {
+    texels
+        .chunks(4)
+        .enumerate()
+        .flat_map(|(i, texel)| {
+            let reference = pattern(i as u32 % TEXTURE_SIZE, i as u32 / TEXTURE_SIZE);
+            texel
+                .iter()
+                .zip(reference)
+                .map(|(&gpu, cpu)| gpu.abs_diff((cpu * 255.0).round() as u8))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Largest difference between the GPU trail and the CPU reference
+fn trail_error(trail: &[f32], frames: u32) -> f32 {
+    trail
+        .iter()
+        .zip(trail_reference(frames))
+        .map(|(gpu, cpu)| (gpu - cpu).abs())
+        .fold(0.0, f32::max)
+}
+
+/// A coarse text rendering of a single channel in 0..1
+fn ascii_preview(value: impl Fn(u32, u32) -> f32) -> String {
+    const SHADES: &[u8] = b" .:-=+*#%@";
+    let (columns, rows) = (32, 16);
+    let mut preview = String::new();
+    for row in 0..rows {
+        preview.push_str("  ");
+        for column in 0..columns {
+            let x = column * TEXTURE_SIZE / columns + TEXTURE_SIZE / columns / 2;
+            let y = row * TEXTURE_SIZE / rows + TEXTURE_SIZE / rows / 2;
+            let shade = (value(x, y).clamp(0.0, 1.0) * (SHADES.len() - 1) as f32).round();
+            preview.push(SHADES[shade as usize] as char);
+        }
+        preview.push('\n');
+    }
+    preview
+}
+
+/// Create GPU adapter, device and queue
+async fn create_device() -> Option<(wgpu::Adapter, wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::all(),
+        ..wgpu::InstanceDescriptor::new_without_display_handle()
+    });
+
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        })
+        .await
+        .ok()?;
+
+    println!("Using adapter: {}", adapter.get_info().name);
+    println!("Backend: {:?}\n", adapter.get_info().backend);
+
+    let (device, queue) = adapter
+        .request_device(&wgpu::DeviceDescriptor {
+            required_features: wgpu::Features::empty(),
+            required_limits: wgpu::Limits::default(),
+            label: Some("Storage Texture Device"),
+            memory_hints: Default::default(),
+            experimental_features: Default::default(),
+            trace: Default::default(),
+        })
+        .await
+        .ok()?;
+    Some((adapter, device, queue))
+}
+
+fn main() {
+    env_logger::init();
+
+    println!("=== Storage Texture Example ===\n");
+    println!(
+        "Compute shaders write a {0}x{0} pattern through storage textures,",
+        TEXTURE_SIZE
+    );
+    println!("first write-only, then read-write in place.\n");
+
+    let Some((adapter, device, queue)) = pollster::block_on(create_device()) else {
+        eprintln!("Failed to create GPU device");
+        return;
+    };
+    println!("✓ GPU device created\n");
+
+    println!("1. Write-only storage texture ({:?})", PATTERN_FORMAT);
+    let texels = write_pattern(&device, &queue);
+    println!("  ✓ Pattern written by one dispatch, red channel:");
+    print!(
+        "{}",
+        ascii_preview(|x, y| texels[((y * TEXTURE_SIZE + x) * 4) as usize] as f32 / 255.0)
+    );
+    let error = pattern_error(&texels);
+    if error <= 2 {
+        println!("  ✓ Matches the CPU reference (max error {}/255)\n", error);
+    } else {
+        println!(
+            "  ✗ Differs from the CPU reference (max error {}/255)\n",
+            error
+        );
+    }
+
+    println!("2. Read-write storage texture ({:?})", TRAIL_FORMAT);
+    if !supports_read_write(&adapter, TRAIL_FORMAT) {
+        println!("  ✗ This adapter doesn't support read-write storage access");
+        println!(
+            "    to {:?}, skipping. Use two textures and swap them",
+            TRAIL_FORMAT
+        );
+        println!("    every frame instead.\n");
+    } else {
+        let trail = paint_trail(&device, &queue, TRAIL_FRAMES);
+        println!(
+            "  ✓ {} frames painted in place, each fading the last by {}:",
+            TRAIL_FRAMES, TRAIL_DECAY
+        );
+        print!(
+            "{}",
+            ascii_preview(|x, y| trail[(y * TEXTURE_SIZE + x) as usize])
+        );
+        let error = trail_error(&trail, TRAIL_FRAMES);
+        if error < 1e-4 {
+            println!("  ✓ Matches the CPU reference (max error {:.2e})\n", error);
+        } else {
+            println!(
+                "  ✗ Differs from the CPU reference (max error {:.2e})\n",
+                error
+            );
+        }
+    }
+
+    println!("=== Example Complete ===\n");
+    println!("Key Concepts:");
+    println!("  • Storage textures need TextureUsages::STORAGE_BINDING");
+    println!("  • Write-only access works with many formats, textureStore() only");
+    println!("  • Read-write access allows textureLoad() and textureStore() on the");
+    println!("    same texture, guaranteed only for r32float, r32uint and r32sint");
+    println!("  • Check Adapter::get_texture_format_features() before relying on it");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_struct_sizes_match_wgsl() {
+        assert_eq!(std::mem::size_of::<Brush>(), 16);
+    }
+
+    #[test]
+    fn test_shaders_use_the_declared_access() {
+        assert!(WRITE_ONLY_SHADER.contains("texture_storage_2d<rgba8unorm, write>"));
+        assert!(READ_WRITE_SHADER.contains("texture_storage_2d<r32float, read_write>"));
+        for shader in [WRITE_ONLY_SHADER, READ_WRITE_SHADER] {
+            assert!(shader.contains(&format!(
+                "@workgroup_size({}, {})",
+                WORKGROUP_SIZE, WORKGROUP_SIZE
+            )));
+        }
+    }
+
+    #[test]
+    fn test_pattern_reference() {
+        // Rings are bright at the center and the checker alternates per cell
+        assert!(pattern(128, 128)[0] > 0.99);
+        assert_eq!(pattern(0, 0)[2], 0.25);
+        assert_eq!(pattern(TEXTURE_SIZE / 8, 0)[2], 1.0);
+        assert!(pattern(TEXTURE_SIZE - 1, 0)[1] > 0.99);
+    }
+
+    #[test]
+    fn test_trail_fades_behind_the_brush() {
+        let trail = trail_reference(TRAIL_FRAMES);
+        let at = |[x, y]: [f32; 2]| {
+            let texel = |c: f32| ((c * TEXTURE_SIZE as f32) as u32).min(TEXTURE_SIZE - 1);
+            trail[(texel(y) * TEXTURE_SIZE + texel(x)) as usize]
+        };
+        let newest = at(brush_for_frame(TRAIL_FRAMES - 1).center);
+        let older = at(brush_for_frame(TRAIL_FRAMES - 4).center);
+        assert!(newest > 0.9, "the brush paints at full strength");
+        assert!(
+            older > 0.1 && older < newest,
+            "older frames fade: {}",
+            older
+        );
+        assert_eq!(at([0.5, 0.5]), 0.0, "the center is never painted");
+    }
+
+    #[tokio::test]
+    async fn test_gpu_storage_textures_match_cpu() {
+        let Some((adapter, device, queue)) = create_device().await else {
+            println!("No GPU available (expected in CI)");
+            return;
+        };
+
+        let texels = write_pattern(&device, &queue);
+        assert!(pattern_error(&texels) <= 2);
+
+        if supports_read_write(&adapter, TRAIL_FORMAT) {
+            let trail = paint_trail(&device, &queue, TRAIL_FRAMES);
+            assert!(trail_error(&trail, TRAIL_FRAMES) < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Generates the C header from the exported functions
+//!
+//! The header is written to `OUT_DIR` on every build. The checked-in copy in
+//! `include/` is only rewritten when `WGPU_PLAYGROUND_REGEN_HEADER=1` is set,
+//! so builds never modify the source tree.
+
+/// Set to `1` to update the checked-in header
+const REGEN_ENV: &str = "WGPU_PLAYGROUND_REGEN_HEADER";
+
+fn main() {
+    let crate_dir =
+        std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed={}", REGEN_ENV);
+
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("cbindgen.toml is valid");
+    match cbindgen::generate_with_config(&crate_dir, config) {
+        Ok(bindings) => {
+            bindings.write_to_file(format!("{}/wgpu_playground.h", out_dir));
+            if std::env::var(REGEN_ENV).is_ok_and(|value| value == "1") {
+                bindings.write_to_file(format!("{}/include/wgpu_playground.h", crate_dir));
+            }
+        }
+        // Keep the previous header when the sources don't parse; rustc
+        // reports the real error.
+        Err(e) => println!("cargo:warning=Skipping header generation: {}", e),
+    }
+}
@@ -0,0 +1,88 @@
+"""Run a compute shader through the playground from Python with ctypes.
+
+Build the library first:
+    cargo build -p wgpu_playground_ffi --release
+Then run:
+    python3 compute_test.py
+"""
+
+import ctypes
+import pathlib
+import struct
+import sys
+
+LIB_NAMES = {
+    "linux": "libwgpu_playground.so",
+    "darwin": "libwgpu_playground.dylib",
+    "win32": "wgpu_playground.dll",
+}
+TARGET_DIR = pathlib.Path(__file__).resolve().parents[3] / "target" / "release"
+
+SHADER = b"""
+@group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x < arrayLength(&data)) {
+        data[id.x] = data[id.x] * data[id.x];
+    }
+}
+"""
+
+STATUS_OK = 0
+
+
+def load_library():
+    lib = ctypes.CDLL(str(TARGET_DIR / LIB_NAMES.get(sys.platform, LIB_NAMES["linux"])))
+    lib.wgpu_playground_session_create.restype = ctypes.c_void_p
+    lib.wgpu_playground_session_create.argtypes = [ctypes.c_char_p]
+    lib.wgpu_playground_session_destroy.argtypes = [ctypes.c_void_p]
+    lib.wgpu_playground_session_set_shader.argtypes = [ctypes.c_void_p, ctypes.c_char_p]
+    lib.wgpu_playground_session_run_compute.argtypes = [
+        ctypes.c_void_p,
+        ctypes.c_char_p,
+        ctypes.c_char_p,
+        ctypes.c_size_t,
+        ctypes.c_char_p,
+        ctypes.c_size_t,
+        ctypes.c_uint32,
+        ctypes.c_uint32,
+        ctypes.c_uint32,
+    ]
+    lib.wgpu_playground_last_error.restype = ctypes.c_char_p
+    return lib
+
+
+def check(lib, status, what):
+    if status != STATUS_OK:
+        error = lib.wgpu_playground_last_error()
+        raise RuntimeError(f"{what} failed ({status}): {error.decode() if error else 'unknown'}")
+
+
+def main():
+    lib = load_library()
+    session = lib.wgpu_playground_session_create(None)
+    if not session:
+        raise RuntimeError(lib.wgpu_playground_last_error().decode())
+    try:
+        check(lib, lib.wgpu_playground_session_set_shader(session, SHADER), "set shader")
+
+        values = list(range(256))
+        data = struct.pack(f"<{len(values)}I", *values)
+        output = ctypes.create_string_buffer(len(data))
+        check(
+            lib,
+            lib.wgpu_playground_session_run_compute(
+                session, b"main", data, len(data), output, len(data), 4, 1, 1
+            ),
+            "run compute",
+        )
+        squares = struct.unpack(f"<{len(values)}I", output.raw)
+        assert list(squares) == [v * v for v in values], "unexpected compute output"
+        print(f"compute test passed: {len(values)} values squared")
+    finally:
+        lib.wgpu_playground_session_destroy(session)
+
+
+if __name__ == "__main__":
+    main()
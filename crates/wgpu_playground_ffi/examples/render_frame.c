@@ -0,0 +1,56 @@
+// Render a playground project from C and compare it with a reference image
+//
+// Build (after `cargo build -p wgpu_playground_ffi --release`):
+//   cc render_frame.c -I ../include -L ../../../target/release -lwgpu_playground -o render_frame
+// Run:
+//   ./render_frame project.json
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "wgpu_playground.h"
+
+#define WIDTH 256
+#define HEIGHT 256
+
+static int fail(const char *what) {
+    const char *error = wgpu_playground_last_error();
+    fprintf(stderr, "%s: %s\n", what, error ? error : "unknown error");
+    return 1;
+}
+
+int main(int argc, char **argv) {
+    if (argc < 2) {
+        fprintf(stderr, "usage: %s <project.json>\n", argv[0]);
+        return 2;
+    }
+
+    WgpuPlaygroundSession *session = wgpu_playground_session_create(NULL);
+    if (!session) {
+        return fail("create session");
+    }
+
+    int result = 0;
+    uint8_t *pixels = malloc(WIDTH * HEIGHT * 4);
+    if (wgpu_playground_session_load_project(session, argv[1]) != WGPU_PLAYGROUND_STATUS_OK) {
+        result = fail("load project");
+    } else if (wgpu_playground_session_render_frame(session, WIDTH, HEIGHT, pixels,
+                                                    WIDTH * HEIGHT * 4) !=
+               WGPU_PLAYGROUND_STATUS_OK) {
+        result = fail("render frame");
+    } else {
+        float difference = 0.0f;
+        WgpuPlaygroundStatus status =
+            wgpu_playground_compare_reference(pixels, WIDTH, HEIGHT, "c_render_frame", 0.01f,
+                                              &difference);
+        if (status != WGPU_PLAYGROUND_STATUS_OK) {
+            result = fail("compare");
+        } else {
+            printf("matches reference (difference %.4f%%)\n", difference * 100.0f);
+        }
+    }
+
+    free(pixels);
+    wgpu_playground_session_destroy(session);
+    return result;
+}
@@ -0,0 +1,140 @@
+#ifndef WGPU_PLAYGROUND_H
+#define WGPU_PLAYGROUND_H
+
+/* Generated by cbindgen from crates/wgpu_playground_ffi. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of every fallible call
+typedef enum WgpuPlaygroundStatus {
+  WGPU_PLAYGROUND_STATUS_OK = 0,
+  WGPU_PLAYGROUND_STATUS_INVALID_ARGUMENT = 1,
+  WGPU_PLAYGROUND_STATUS_NO_ADAPTER = 2,
+  WGPU_PLAYGROUND_STATUS_PROJECT = 3,
+  WGPU_PLAYGROUND_STATUS_SHADER = 4,
+  WGPU_PLAYGROUND_STATUS_GPU = 5,
+  WGPU_PLAYGROUND_STATUS_BUFFER_TOO_SMALL = 6,
+  // The reference image could not be loaded or saved
+  WGPU_PLAYGROUND_STATUS_REFERENCE = 7,
+  // The images differ by more than the threshold
+  WGPU_PLAYGROUND_STATUS_MISMATCH = 8,
+  WGPU_PLAYGROUND_STATUS_PANIC = 9,
+} WgpuPlaygroundStatus;
+
+// Opaque session handle
+typedef struct WgpuPlaygroundSession WgpuPlaygroundSession;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create a headless session
+//
+// `backend` selects the wgpu backends ("vulkan", "metal", "dx12", "gl",
+// "primary", "all"); null means all backends. A software adapter is used
+// when no hardware adapter is found. Returns null on failure; see
+// [`wgpu_playground_last_error`].
+//
+// # Safety
+//
+// `backend` must be null or a NUL-terminated string.
+WgpuPlaygroundSession *wgpu_playground_session_create(const char *backend);
+
+// Destroy a session; null is ignored
+//
+// # Safety
+//
+// `session` must be null or a live pointer from
+// [`wgpu_playground_session_create`]. It must not be used afterwards.
+void wgpu_playground_session_destroy(WgpuPlaygroundSession *session);
+
+// Load a project saved by the playground (File → Save)
+//
+// The project's shader and pipeline entry points are used by the render
+// and compute calls.
+//
+// # Safety
+//
+// `session` must be a live session and `path` a NUL-terminated string.
+WgpuPlaygroundStatus wgpu_playground_session_load_project(WgpuPlaygroundSession *session,
+                                                          const char *path);
+
+// Replace the session shader with WGSL source
+//
+// # Safety
+//
+// `session` must be a live session and `source` a NUL-terminated string.
+WgpuPlaygroundStatus wgpu_playground_session_set_shader(WgpuPlaygroundSession *session,
+                                                        const char *source);
+
+// Render one frame of the session shader into `out_pixels`
+//
+// Draws three vertices with the vertex/fragment entry points and writes
+// `width * height * 4` bytes of tightly packed sRGB RGBA8 pixels, row by
+// row from the top.
+//
+// # Safety
+//
+// `session` must be a live session and `out_pixels` valid for writes of
+// `out_len` bytes.
+WgpuPlaygroundStatus wgpu_playground_session_render_frame(WgpuPlaygroundSession *session,
+                                                          uint32_t width,
+                                                          uint32_t height,
+                                                          uint8_t *out_pixels,
+                                                          size_t out_len);
+
+// Run a compute entry point over one storage buffer
+//
+// `input` (may be null when `input_len` is 0) is uploaded to the
+// `read_write` storage buffer at `@group(0) @binding(0)`, the entry point
+// is dispatched with `workgroups_x/y/z`, and the first `output_len` bytes
+// of the buffer are written to `output`. A null `entry_point` uses the
+// project's compute pipeline entry point or the shader's first one.
+//
+// # Safety
+//
+// `session` must be a live session, `entry_point` null or NUL-terminated,
+// `input` valid for reads of `input_len` bytes and `output` valid for
+// writes of `output_len` bytes.
+WgpuPlaygroundStatus wgpu_playground_session_run_compute(WgpuPlaygroundSession *session,
+                                                         const char *entry_point,
+                                                         const uint8_t *input,
+                                                         size_t input_len,
+                                                         uint8_t *output,
+                                                         size_t output_len,
+                                                         uint32_t workgroups_x,
+                                                         uint32_t workgroups_y,
+                                                         uint32_t workgroups_z);
+
+// Compare RGBA8 pixels with the reference image for `test_name`
+//
+// Uses the playground's visual regression references and report.
+// `out_difference` (may be null) receives the difference in `0.0..=1.0`.
+// Returns [`WgpuPlaygroundStatus::Mismatch`] if it exceeds `threshold`.
+//
+// # Safety
+//
+// `pixels` must be valid for reads of `width * height * 4` bytes,
+// `test_name` NUL-terminated, and `out_difference` null or writable.
+WgpuPlaygroundStatus wgpu_playground_compare_reference(const uint8_t *pixels,
+                                                       uint32_t width,
+                                                       uint32_t height,
+                                                       const char *test_name,
+                                                       float threshold,
+                                                       float *out_difference);
+
+// Description of the last error on this thread, or null
+//
+// The string is owned by the library and valid until the next call on the
+// same thread.
+const char *wgpu_playground_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WGPU_PLAYGROUND_H */
@@ -0,0 +1,437 @@
+//! C ABI for the playground's headless rendering and regression machinery
+//!
+//! Lets C, C++ and Python (via `ctypes`/`cffi`) harnesses create a headless
+//! session, load a saved playground project, render frames into their own
+//! buffers, run compute tests and compare frames against reference images.
+//! The generated header lives at `include/wgpu_playground.h`.
+//!
+//! Conventions:
+//!
+//! - Functions return a [`WgpuPlaygroundStatus`]; on failure, a description
+//!   is available from [`wgpu_playground_last_error`] on the same thread.
+//! - Strings are NUL-terminated UTF-8. Output buffers are owned by the
+//!   caller; their required size is documented per function.
+//! - Panics never unwind into the caller; they are reported as
+//!   [`WgpuPlaygroundStatus::Panic`].
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+
+pub use wgpu_playground_core::headless_session::{Session, SessionError, FRAME_FORMAT};
+
+/// Result of every fallible call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WgpuPlaygroundStatus {
+    Ok = 0,
+    InvalidArgument = 1,
+    NoAdapter = 2,
+    Project = 3,
+    Shader = 4,
+    Gpu = 5,
+    BufferTooSmall = 6,
+    /// The reference image could not be loaded or saved
+    Reference = 7,
+    /// The images differ by more than the threshold
+    Mismatch = 8,
+    Panic = 9,
+}
+
+impl From<&SessionError> for WgpuPlaygroundStatus {
+    fn from(error: &SessionError) -> Self {
+        match error {
+            SessionError::InvalidArgument(_) => WgpuPlaygroundStatus::InvalidArgument,
+            SessionError::NoAdapter(_) => WgpuPlaygroundStatus::NoAdapter,
+            SessionError::Project(_) => WgpuPlaygroundStatus::Project,
+            SessionError::Shader(_) => WgpuPlaygroundStatus::Shader,
+            SessionError::Gpu(_) => WgpuPlaygroundStatus::Gpu,
+            SessionError::BufferTooSmall { .. } => WgpuPlaygroundStatus::BufferTooSmall,
+            SessionError::Reference(_) => WgpuPlaygroundStatus::Reference,
+        }
+    }
+}
+
+/// Opaque session handle
+pub struct WgpuPlaygroundSession {
+    inner: Session,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    // Interior NULs would truncate the message on the C side anyway
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
+/// Run `f`, converting errors and panics into a status
+fn ffi_call(f: impl FnOnce() -> Result<(), SessionError>) -> WgpuPlaygroundStatus {
+    clear_last_error();
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => WgpuPlaygroundStatus::Ok,
+        Ok(Err(error)) => {
+            let status = WgpuPlaygroundStatus::from(&error);
+            set_last_error(error.to_string());
+            status
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(format!("Panic: {}", message));
+            WgpuPlaygroundStatus::Panic
+        }
+    }
+}
+
+/// Borrow an optional C string
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn optional_str<'a>(
+    ptr: *const c_char,
+    what: &str,
+) -> Result<Option<&'a str>, SessionError> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(Some)
+        .map_err(|_| SessionError::InvalidArgument(format!("{} is not valid UTF-8", what)))
+}
+
+/// Borrow a required C string
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn required_str<'a>(ptr: *const c_char, what: &str) -> Result<&'a str, SessionError> {
+    optional_str(ptr, what)?
+        .ok_or_else(|| SessionError::InvalidArgument(format!("{} is null", what)))
+}
+
+/// # Safety
+///
+/// `session` must be null or a pointer returned by
+/// [`wgpu_playground_session_create`] that has not been destroyed.
+unsafe fn session_mut<'a>(
+    session: *mut WgpuPlaygroundSession,
+) -> Result<&'a mut Session, SessionError> {
+    session
+        .as_mut()
+        .map(|s| &mut s.inner)
+        .ok_or_else(|| SessionError::InvalidArgument("session is null".into()))
+}
+
+/// Copy `data` into a caller-provided buffer
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes of `out_len` bytes.
+unsafe fn write_output(data: &[u8], out: *mut u8, out_len: usize) -> Result<(), SessionError> {
+    if out.is_null() {
+        return Err(SessionError::InvalidArgument(
+            "output buffer is null".into(),
+        ));
+    }
+    if out_len < data.len() {
+        return Err(SessionError::BufferTooSmall {
+            required: data.len(),
+            provided: out_len,
+        });
+    }
+    ptr::copy_nonoverlapping(data.as_ptr(), out, data.len());
+    Ok(())
+}
+
+/// Create a headless session
+///
+/// `backend` selects the wgpu backends ("vulkan", "metal", "dx12", "gl",
+/// "primary", "all"); null means all backends. A software adapter is used
+/// when no hardware adapter is found. Returns null on failure; see
+/// [`wgpu_playground_last_error`].
+///
+/// # Safety
+///
+/// `backend` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wgpu_playground_session_create(
+    backend: *const c_char,
+) -> *mut WgpuPlaygroundSession {
+    let mut created = None;
+    ffi_call(|| {
+        let backends = match optional_str(backend, "backend")? {
+            Some(name) => wgpu_playground_core::adapter::parse_backends(name).ok_or_else(|| {
+                SessionError::InvalidArgument(format!("Unknown backend '{}'", name))
+            })?,
+            None => wgpu::Backends::all(),
+        };
+        created = Some(Session::new(backends)?);
+        Ok(())
+    });
+    created.map_or(ptr::null_mut(), |inner| {
+        Box::into_raw(Box::new(WgpuPlaygroundSession { inner }))
+    })
+}
+
+/// Destroy a session; null is ignored
+///
+/// # Safety
+///
+/// `session` must be null or a live pointer from
+/// [`wgpu_playground_session_create`]. It must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn wgpu_playground_session_destroy(session: *mut WgpuPlaygroundSession) {
+    if !session.is_null() {
+        drop(Box::from_raw(session));
+    }
+}
+
+/// Load a project saved by the playground (File → Save)
+///
+/// The project's shader and pipeline entry points are used by the render
+/// and compute calls.
+///
+/// # Safety
+///
+/// `session` must be a live session and `path` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wgpu_playground_session_load_project(
+    session: *mut WgpuPlaygroundSession,
+    path: *const c_char,
+) -> WgpuPlaygroundStatus {
+    ffi_call(|| {
+        let session = session_mut(session)?;
+        let path = required_str(path, "path")?;
+        session.load_project(Path::new(path))
+    })
+}
+
+/// Replace the session shader with WGSL source
+///
+/// # Safety
+///
+/// `session` must be a live session and `source` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wgpu_playground_session_set_shader(
+    session: *mut WgpuPlaygroundSession,
+    source: *const c_char,
+) -> WgpuPlaygroundStatus {
+    ffi_call(|| {
+        let session = session_mut(session)?;
+        session.set_shader(required_str(source, "source")?);
+        Ok(())
+    })
+}
+
+/// Render one frame of the session shader into `out_pixels`
+///
+/// Draws three vertices with the vertex/fragment entry points and writes
+/// `width * height * 4` bytes of tightly packed sRGB RGBA8 pixels, row by
+/// row from the top.
+///
+/// # Safety
+///
+/// `session` must be a live session and `out_pixels` valid for writes of
+/// `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wgpu_playground_session_render_frame(
+    session: *mut WgpuPlaygroundSession,
+    width: u32,
+    height: u32,
+    out_pixels: *mut u8,
+    out_len: usize,
+) -> WgpuPlaygroundStatus {
+    ffi_call(|| {
+        let session = session_mut(session)?;
+        let required = width as usize * height as usize * 4;
+        if !out_pixels.is_null() && out_len < required {
+            return Err(SessionError::BufferTooSmall {
+                required,
+                provided: out_len,
+            });
+        }
+        let pixels = session.render_frame(width, height)?;
+        write_output(&pixels, out_pixels, out_len)
+    })
+}
+
+/// Run a compute entry point over one storage buffer
+///
+/// `input` (may be null when `input_len` is 0) is uploaded to the
+/// `read_write` storage buffer at `@group(0) @binding(0)`, the entry point
+/// is dispatched with `workgroups_x/y/z`, and the first `output_len` bytes
+/// of the buffer are written to `output`. A null `entry_point` uses the
+/// project's compute pipeline entry point or the shader's first one.
+///
+/// # Safety
+///
+/// `session` must be a live session, `entry_point` null or NUL-terminated,
+/// `input` valid for reads of `input_len` bytes and `output` valid for
+/// writes of `output_len` bytes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn wgpu_playground_session_run_compute(
+    session: *mut WgpuPlaygroundSession,
+    entry_point: *const c_char,
+    input: *const u8,
+    input_len: usize,
+    output: *mut u8,
+    output_len: usize,
+    workgroups_x: u32,
+    workgroups_y: u32,
+    workgroups_z: u32,
+) -> WgpuPlaygroundStatus {
+    ffi_call(|| {
+        let session = session_mut(session)?;
+        let entry_point = optional_str(entry_point, "entry_point")?;
+        let input = match (input.is_null(), input_len) {
+            (_, 0) => &[][..],
+            (true, _) => return Err(SessionError::InvalidArgument("input is null".into())),
+            (false, len) => std::slice::from_raw_parts(input, len),
+        };
+        let result = session.run_compute(
+            entry_point,
+            input,
+            output_len,
+            [workgroups_x, workgroups_y, workgroups_z],
+        )?;
+        write_output(&result, output, output_len)
+    })
+}
+
+/// Compare RGBA8 pixels with the reference image for `test_name`
+///
+/// Uses the playground's visual regression references and report.
+/// `out_difference` (may be null) receives the difference in `0.0..=1.0`.
+/// Returns [`WgpuPlaygroundStatus::Mismatch`] if it exceeds `threshold`.
+///
+/// # Safety
+///
+/// `pixels` must be valid for reads of `width * height * 4` bytes,
+/// `test_name` NUL-terminated, and `out_difference` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn wgpu_playground_compare_reference(
+    pixels: *const u8,
+    width: u32,
+    height: u32,
+    test_name: *const c_char,
+    threshold: f32,
+    out_difference: *mut f32,
+) -> WgpuPlaygroundStatus {
+    let mut matched = true;
+    let status = ffi_call(|| {
+        let test_name = required_str(test_name, "test_name")?;
+        if pixels.is_null() {
+            return Err(SessionError::InvalidArgument("pixels is null".into()));
+        }
+        let len = width as usize * height as usize * 4;
+        let data = std::slice::from_raw_parts(pixels, len).to_vec();
+        let image = image::RgbaImage::from_raw(width, height, data)
+            .ok_or_else(|| SessionError::InvalidArgument("pixel buffer size mismatch".into()))?;
+        let config = wgpu_playground_core::visual_regression::ComparisonConfig {
+            threshold,
+            ..Default::default()
+        };
+        let result = wgpu_playground_core::visual_regression::compare_with_reference(
+            &image, test_name, config,
+        )
+        .map_err(|e| SessionError::Reference(e.to_string()))?;
+        if let Some(out) = out_difference.as_mut() {
+            *out = result.difference;
+        }
+        matched = result.is_match;
+        if !matched {
+            set_last_error(format!(
+                "'{}' differs by {:.4}% (threshold {:.4}%)",
+                test_name,
+                result.difference * 100.0,
+                threshold * 100.0
+            ));
+        }
+        Ok(())
+    });
+    match status {
+        WgpuPlaygroundStatus::Ok if !matched => WgpuPlaygroundStatus::Mismatch,
+        status => status,
+    }
+}
+
+/// Description of the last error on this thread, or null
+///
+/// The string is owned by the library and valid until the next call on the
+/// same thread.
+#[no_mangle]
+pub extern "C" fn wgpu_playground_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| slot.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> Option<String> {
+        let ptr = wgpu_playground_last_error();
+        (!ptr.is_null()).then(|| {
+            unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    #[test]
+    fn test_null_session_is_rejected() {
+        let status = unsafe {
+            wgpu_playground_session_render_frame(ptr::null_mut(), 4, 4, ptr::null_mut(), 0)
+        };
+        assert_eq!(status, WgpuPlaygroundStatus::InvalidArgument);
+        assert_eq!(
+            last_error().as_deref(),
+            Some("Invalid argument: session is null")
+        );
+    }
+
+    #[test]
+    fn test_unknown_backend() {
+        let backend = CString::new("glide").unwrap();
+        let session = unsafe { wgpu_playground_session_create(backend.as_ptr()) };
+        assert!(session.is_null());
+        assert!(last_error().unwrap().contains("Unknown backend 'glide'"));
+    }
+
+    #[test]
+    fn test_panics_become_status() {
+        let status = ffi_call(|| panic!("boom"));
+        assert_eq!(status, WgpuPlaygroundStatus::Panic);
+        assert_eq!(last_error().as_deref(), Some("Panic: boom"));
+
+        assert_eq!(ffi_call(|| Ok(())), WgpuPlaygroundStatus::Ok);
+        assert_eq!(last_error(), None);
+    }
+
+    #[test]
+    fn test_output_buffer_too_small() {
+        let mut out = [0u8; 2];
+        let result = unsafe { write_output(&[1, 2, 3], out.as_mut_ptr(), out.len()) };
+        assert_eq!(
+            result,
+            Err(SessionError::BufferTooSmall {
+                required: 3,
+                provided: 2
+            })
+        );
+    }
+}
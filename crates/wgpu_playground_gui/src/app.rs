@@ -2,64 +2,73 @@ use wgpu_playground_core::adapter_selection::AdapterSelectionPanel;
 use wgpu_playground_core::api_coverage::ApiCoverageTracker;
 use wgpu_playground_core::api_coverage_panel::{ApiCoveragePanel, NavigationRequest};
 use wgpu_playground_core::api_reference_panel::ApiReferencePanel;
-use wgpu_playground_core::bind_group_layout_panel::BindGroupLayoutPanel;
-use wgpu_playground_core::bind_group_panel::BindGroupPanel;
-use wgpu_playground_core::buffer_inspector::BufferInspector;
-use wgpu_playground_core::buffer_panel::BufferPanel;
-use wgpu_playground_core::command_recording_panel::CommandRecordingPanel;
-use wgpu_playground_core::compute::ComputePanel;
-use wgpu_playground_core::compute_dispatch_panel::ComputeDispatchPanel;
-use wgpu_playground_core::compute_pipeline_panel::ComputePipelinePanel;
+use wgpu_playground_core::clipboard_image::BASE_DPI;
+use wgpu_playground_core::command_palette::{Command, CommandKind, CommandPalette, CommandRegistry};
 use wgpu_playground_core::console::ConsolePanel;
-use wgpu_playground_core::device_config::DeviceConfigPanel;
+use wgpu_playground_core::cpu_profiler::CpuProfiler;
+use wgpu_playground_core::cpu_profiler_panel::CpuProfilerPanel;
+use wgpu_playground_core::device_config::{DeviceConfig, DeviceConfigPanel};
 use wgpu_playground_core::device_info::DeviceInfo;
-use wgpu_playground_core::draw_command_panel::DrawCommandPanel;
+use wgpu_playground_core::device_recovery::{DeviceLoss, ResourceRecreate};
+use wgpu_playground_core::drop_router::{self, DropTarget};
+use wgpu_playground_core::exercise_panel::ExercisePanel;
+use wgpu_playground_core::frame_pacing::{FrameTimer, PresentSettings};
+use wgpu_playground_core::frame_pacing_panel::FramePacingPanel;
+use wgpu_playground_core::i18n::{self, tr, tr_args};
+use wgpu_playground_core::job_system::JobSystem;
+use wgpu_playground_core::keybindings::Action;
+use wgpu_playground_core::keybindings_panel::KeyBindingsPanel;
 use wgpu_playground_core::learning_path_panel::LearningPathPanel;
-use wgpu_playground_core::model_loader_panel::ModelLoaderPanel;
+use wgpu_playground_core::lesson_panel::{LessonAction, LessonPanel};
+use wgpu_playground_core::memory_budget::{BudgetLevel, MemoryBudget};
 use wgpu_playground_core::performance_panel::PerformancePanel;
-use wgpu_playground_core::pipeline_debugger::PipelineDebugger;
+use wgpu_playground_core::permalink::{self, PermalinkError};
+use wgpu_playground_core::preset::get_preset;
 use wgpu_playground_core::preset_panel::PresetPanel;
-use wgpu_playground_core::render_pass_panel::RenderPassPanel;
-use wgpu_playground_core::render_pipeline_panel::RenderPipelinePanel;
-use wgpu_playground_core::rendering::RenderingPanel;
-use wgpu_playground_core::resource_inspector::ResourceInspectorPanel;
-use wgpu_playground_core::sampler_panel::SamplerPanel;
+use wgpu_playground_core::project_template::ProjectTemplate;
+use wgpu_playground_core::puffin_viewer_panel::PuffinViewerPanel;
+use wgpu_playground_core::renderdoc_capture::{capture_label, RenderDocCapture};
 use wgpu_playground_core::settings_panel::SettingsPanel;
 use wgpu_playground_core::state::Theme;
-use wgpu_playground_core::texture_inspector::TextureInspector;
-use wgpu_playground_core::texture_panel::TexturePanel;
+use wgpu_playground_core::surface_config::SurfaceSettings;
+use wgpu_playground_core::surface_config_panel::{OverlayTarget, SurfaceConfigPanel};
+use wgpu_playground_core::theme;
+use wgpu_playground_core::tutorial::TutorialState;
 use wgpu_playground_core::tutorial_panel::TutorialPanel;
 
+use crate::workspace::{next_workspace_name, Workspace};
+
 pub struct PlaygroundApp {
     device_info: DeviceInfo,
     device_config: DeviceConfigPanel,
+    surface_config_panel: SurfaceConfigPanel,
     adapter_selection: AdapterSelectionPanel,
-    rendering_panel: RenderingPanel,
-    compute_panel: ComputePanel,
-    compute_pipeline_panel: ComputePipelinePanel,
-    compute_dispatch_panel: ComputeDispatchPanel,
-    buffer_panel: BufferPanel,
-    sampler_panel: SamplerPanel,
-    texture_panel: TexturePanel,
-    bind_group_panel: BindGroupPanel,
-    bind_group_layout_panel: BindGroupLayoutPanel,
-    render_pipeline_panel: RenderPipelinePanel,
-    model_loader_panel: ModelLoaderPanel,
     console_panel: ConsolePanel,
-    draw_command_panel: DrawCommandPanel,
-    render_pass_panel: RenderPassPanel,
-    resource_inspector_panel: ResourceInspectorPanel,
-    buffer_inspector: BufferInspector,
-    texture_inspector: TextureInspector,
-    pipeline_debugger: PipelineDebugger,
     performance_panel: PerformancePanel,
-    command_recording_panel: CommandRecordingPanel,
+    frame_pacing_panel: FramePacingPanel,
+    cpu_profiler_panel: CpuProfilerPanel,
+    puffin_viewer_panel: PuffinViewerPanel,
     settings_panel: SettingsPanel,
+    /// Keyboard shortcuts of the playground actions and their settings page
+    keybindings_panel: KeyBindingsPanel,
+    /// Fuzzy search over the commands the panels register (Ctrl+P)
+    command_palette: CommandPalette,
     api_coverage_panel: ApiCoveragePanel,
     api_reference_panel: ApiReferencePanel,
     tutorial_panel: TutorialPanel,
+    /// Interactive lessons, shown in a side column while one is in progress
+    lesson_panel: LessonPanel,
+    /// Assertions from an exercise file, checked against the active workspace
+    exercise_panel: ExercisePanel,
     preset_panel: PresetPanel,
     learning_path_panel: LearningPathPanel,
+    /// GPU memory usage against the budget, shared by all workspaces
+    memory_budget: MemoryBudget,
+    /// Independent configurations shown as tabs; never empty
+    workspaces: Vec<Workspace>,
+    active_workspace: usize,
+    /// Workspace whose name is being edited
+    renaming_workspace: Option<usize>,
     selected_tab: Tab,
     // Collapsible section states
     setup_section_open: bool,
@@ -73,12 +82,56 @@ pub struct PlaygroundApp {
     // URL sharing fields
     share_url: String,
     share_message: Option<String>,
+    /// Text pasted into the permalink import dialog, `Some` while it is open
+    permalink_input: Option<String>,
+    /// Choices of the New Project dialog, `Some` while it is open
+    new_project: Option<NewProjectDialog>,
+    // Screenshot fields
+    screenshot_dir: String,
+    screenshot_requested: bool,
+    /// Ctrl+V pressed outside text fields, pasting a clipboard image
+    clipboard_paste_requested: bool,
+    /// Frame captures when launched from RenderDoc
+    renderdoc: RenderDocCapture,
+    device_loss_requested: bool,
+    toast: Option<Toast>,
+    /// Whether a theme change still has to be saved
+    theme_save_pending: bool,
+}
+
+/// Short-lived notification shown in the bottom-right corner
+struct Toast {
+    message: String,
+    success: bool,
+    /// `egui::InputState::time` after which the toast disappears
+    expires_at: f64,
+}
+
+/// Choices made in the New Project dialog
+struct NewProjectDialog {
+    template: ProjectTemplate,
+    name: String,
+    /// Open the project in a new workspace rather than replacing the active
+    /// one
+    new_workspace: bool,
+}
+
+/// Change to the workspace list requested from the tab strip
+enum WorkspaceAction {
+    Switch(usize),
+    New,
+    Duplicate(usize),
+    Close(usize),
 }
 
+/// How long a toast stays visible, in seconds
+const TOAST_DURATION: f64 = 4.0;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Tab {
     AdapterSelection,
     DeviceConfig,
+    SurfaceConfig,
     DeviceInfo,
     Rendering,
     BufferConfig,
@@ -90,15 +143,28 @@ enum Tab {
     RenderPipelineConfig,
     DrawCommand,
     RenderPassConfig,
+    RenderTargets,
+    FragmentPlayground,
     ComputeDispatch,
+    AsyncCompute,
+    WorkgroupTuner,
     Compute,
     Console,
     ResourceInspector,
     BufferInspector,
     TextureInspector,
     PipelineDebugger,
+    DebugCounters,
+    ShaderVariants,
+    ShaderTranslation,
+    ExternalComparison,
+    ExerciseChecks,
     Performance,
+    FramePacing,
+    CpuProfiler,
+    PuffinViewer,
     CommandRecording,
+    KeyBindings,
     Settings,
     ModelLoader,
     ApiCoverage,
@@ -106,6 +172,120 @@ enum Tab {
     Tutorials,
     Presets,
     LearningPath,
+    History,
+}
+
+impl Tab {
+    /// All tabs, in sidebar order
+    const ALL: [Tab; 44] = [
+        Tab::AdapterSelection,
+        Tab::DeviceConfig,
+        Tab::SurfaceConfig,
+        Tab::DeviceInfo,
+        Tab::Rendering,
+        Tab::RenderPipelineConfig,
+        Tab::RenderPassConfig,
+        Tab::RenderTargets,
+        Tab::DrawCommand,
+        Tab::FragmentPlayground,
+        Tab::Compute,
+        Tab::ComputePipelineConfig,
+        Tab::ComputeDispatch,
+        Tab::AsyncCompute,
+        Tab::WorkgroupTuner,
+        Tab::BufferConfig,
+        Tab::TextureConfig,
+        Tab::SamplerConfig,
+        Tab::ModelLoader,
+        Tab::BindGroupConfig,
+        Tab::BindGroupLayoutConfig,
+        Tab::Tutorials,
+        Tab::LearningPath,
+        Tab::Presets,
+        Tab::History,
+        Tab::ResourceInspector,
+        Tab::BufferInspector,
+        Tab::TextureInspector,
+        Tab::PipelineDebugger,
+        Tab::DebugCounters,
+        Tab::ShaderVariants,
+        Tab::ShaderTranslation,
+        Tab::ExternalComparison,
+        Tab::ExerciseChecks,
+        Tab::CommandRecording,
+        Tab::ApiCoverage,
+        Tab::ApiReference,
+        Tab::Console,
+        Tab::Performance,
+        Tab::FramePacing,
+        Tab::CpuProfiler,
+        Tab::PuffinViewer,
+        Tab::KeyBindings,
+        Tab::Settings,
+    ];
+
+    /// Message key of the tab's sidebar entry
+    fn nav_key(&self) -> &'static str {
+        match self {
+            Tab::AdapterSelection => "nav-adapter-selection",
+            Tab::DeviceConfig => "nav-device-config",
+            Tab::SurfaceConfig => "nav-surface-config",
+            Tab::DeviceInfo => "nav-device-info",
+            Tab::Rendering => "nav-examples",
+            Tab::RenderPipelineConfig => "nav-render-pipeline",
+            Tab::RenderPassConfig => "nav-render-pass",
+            Tab::RenderTargets => "nav-render-targets",
+            Tab::DrawCommand => "nav-draw-commands",
+            Tab::FragmentPlayground => "nav-fragment-playground",
+            Tab::Compute => "nav-compute-panel",
+            Tab::ComputePipelineConfig => "nav-compute-pipeline",
+            Tab::ComputeDispatch => "nav-compute-dispatch",
+            Tab::AsyncCompute => "nav-async-compute",
+            Tab::WorkgroupTuner => "nav-workgroup-tuner",
+            Tab::BufferConfig => "nav-buffers",
+            Tab::TextureConfig => "nav-textures",
+            Tab::SamplerConfig => "nav-samplers",
+            Tab::ModelLoader => "nav-models",
+            Tab::BindGroupConfig => "nav-bind-groups",
+            Tab::BindGroupLayoutConfig => "nav-bind-group-layouts",
+            Tab::Tutorials => "nav-tutorials",
+            Tab::LearningPath => "nav-learning-path",
+            Tab::Presets => "nav-presets",
+            Tab::History => "nav-history",
+            Tab::ResourceInspector => "nav-resource-inspector",
+            Tab::BufferInspector => "nav-buffer-inspector",
+            Tab::TextureInspector => "nav-texture-inspector",
+            Tab::PipelineDebugger => "nav-pipeline-debugger",
+            Tab::DebugCounters => "nav-debug-counters",
+            Tab::ShaderVariants => "nav-shader-variants",
+            Tab::ShaderTranslation => "nav-shader-translation",
+            Tab::ExternalComparison => "nav-external-comparison",
+            Tab::ExerciseChecks => "nav-exercise-checks",
+            Tab::CommandRecording => "nav-command-recording",
+            Tab::ApiCoverage => "nav-api-coverage",
+            Tab::ApiReference => "nav-api-reference",
+            Tab::Console => "nav-console",
+            Tab::Performance => "nav-performance",
+            Tab::FramePacing => "nav-frame-pacing",
+            Tab::CpuProfiler => "nav-cpu-profiler",
+            Tab::PuffinViewer => "nav-puffin-viewer",
+            Tab::KeyBindings => "nav-keybindings",
+            Tab::Settings => "nav-settings",
+        }
+    }
+
+    /// Action whose keyboard shortcut switches to the tab
+    fn show_action(&self) -> Option<Action> {
+        match self {
+            Tab::Rendering => Some(Action::ShowRendering),
+            Tab::Compute => Some(Action::ShowCompute),
+            Tab::BufferConfig => Some(Action::ShowBuffers),
+            Tab::TextureConfig => Some(Action::ShowTextures),
+            Tab::Console => Some(Action::ShowConsole),
+            Tab::Settings => Some(Action::ShowSettings),
+            _ => None,
+        }
+    }
 }
 
 impl PlaygroundApp {
@@ -118,33 +298,27 @@ impl PlaygroundApp {
         Self {
             device_info: DeviceInfo::new(adapter, device),
             device_config: DeviceConfigPanel::new(adapter),
+            surface_config_panel: SurfaceConfigPanel::new(),
             adapter_selection: AdapterSelectionPanel::new(adapter),
-            rendering_panel: RenderingPanel::new(device, queue),
-            compute_panel: ComputePanel::new(),
-            compute_pipeline_panel: ComputePipelinePanel::new(),
-            compute_dispatch_panel: ComputeDispatchPanel::new(),
-            buffer_panel: BufferPanel::new(),
-            sampler_panel: SamplerPanel::new(),
-            texture_panel: TexturePanel::new(),
-            bind_group_panel: BindGroupPanel::new(),
-            bind_group_layout_panel: BindGroupLayoutPanel::new(),
-            render_pipeline_panel: RenderPipelinePanel::new(),
-            model_loader_panel: ModelLoaderPanel::new(),
             console_panel,
-            draw_command_panel: DrawCommandPanel::new(),
-            render_pass_panel: RenderPassPanel::new(),
-            resource_inspector_panel: ResourceInspectorPanel::new(),
-            buffer_inspector: BufferInspector::new(),
-            texture_inspector: TextureInspector::new(),
-            pipeline_debugger: PipelineDebugger::new(),
             performance_panel: PerformancePanel::new(),
-            command_recording_panel: CommandRecordingPanel::new(),
+            frame_pacing_panel: FramePacingPanel::new(),
+            cpu_profiler_panel: CpuProfilerPanel::new(),
+            puffin_viewer_panel: PuffinViewerPanel::new(),
             settings_panel: SettingsPanel::new(),
+            keybindings_panel: KeyBindingsPanel::new(),
+            command_palette: CommandPalette::new(),
             api_coverage_panel: ApiCoveragePanel::new(),
             api_reference_panel: ApiReferencePanel::new(),
             tutorial_panel: TutorialPanel::new(),
+            lesson_panel: LessonPanel::new(),
+            exercise_panel: ExercisePanel::new(),
             preset_panel: PresetPanel::new(),
             learning_path_panel: LearningPathPanel::new(),
+            memory_budget: MemoryBudget::new(),
+            workspaces: vec![Workspace::new("Workspace 1", device, queue)],
+            active_workspace: 0,
+            renaming_workspace: None,
             selected_tab: Tab::Rendering, // Start with Rendering tab to show visual example
             // Initialize section states - Rendering open by default
             setup_section_open: false,
@@ -156,6 +330,15 @@ impl PlaygroundApp {
             save_load_message: None,
             share_url: String::new(),
             share_message: None,
+            permalink_input: None,
+            new_project: None,
+            screenshot_dir: wgpu_playground_core::frame_capture::DEFAULT_SCREENSHOT_DIR.to_string(),
+            screenshot_requested: false,
+            clipboard_paste_requested: false,
+            renderdoc: RenderDocCapture::new(),
+            device_loss_requested: false,
+            toast: None,
+            theme_save_pending: false,
         }
     }
 
@@ -169,48 +352,83 @@ impl PlaygroundApp {
         let ctx = ui.ctx().clone();
         // Update performance metrics each frame
         self.performance_panel.update();
+        // Artificial frame cost from the Frame Pacing tab
+        {
+            wgpu_playground_core::cpu_scope!("Artificial load");
+            self.frame_pacing_panel.apply_load(device, queue);
+        }
 
-        // Keyboard shortcuts for accessibility
-        ctx.input(|i| {
-            // Ctrl+S or Cmd+S: Save state
-            if i.modifiers.command && i.key_pressed(egui::Key::S) {
-                let filename = self.save_load_filename.clone();
-                let path = std::path::Path::new(&filename);
-                if let Err(e) = self.save_state_to_file(path) {
-                    log::error!("Failed to save state: {}", e);
-                } else {
-                    log::info!("State saved to {}", filename);
-                }
+        // Measure GPU memory against the budget, warning once per crossing
+        let tracked = self
+            .workspaces
+            .iter()
+            .map(|workspace| workspace.resource_inspector_panel.tracked_memory())
+            .sum();
+        if self.memory_budget.refresh(device, tracked).is_some() {
+            if let Some(warning) = self.memory_budget.warning() {
+                self.console_panel.warning(warning.clone());
+                self.toast = Some(Toast {
+                    message: format!("⚠ {}", warning),
+                    success: false,
+                    expires_at: ctx.input(|i| i.time) + TOAST_DURATION,
+                });
             }
-
-            // Ctrl+O or Cmd+O: Load state
-            if i.modifiers.command && i.key_pressed(egui::Key::O) {
-                let filename = self.save_load_filename.clone();
-                let path = std::path::Path::new(&filename);
-                if let Err(e) = self.load_state_from_file(path) {
-                    log::error!("Failed to load state: {}", e);
-                } else {
-                    log::info!("State loaded from {}", filename);
-                }
+        }
+        self.workspaces[self.active_workspace]
+            .buffer_panel
+            .set_memory_budget(&self.memory_budget);
+
+        // Report asset changes and follow assets directory switches
+        for event in self.settings_panel.poll_asset_changes() {
+            self.console_panel.info(format!(
+                "Asset {:?}: {}/{}",
+                event.change,
+                event.asset.subdir(),
+                event.filename
+            ));
+        }
+        if self.settings_panel.take_assets_dir_changed() {
+            for workspace in &mut self.workspaces {
+                workspace.rendering_panel.restart_shader_watcher();
             }
+            self.console_panel.info(format!(
+                "Assets directory: {}",
+                wgpu_playground_core::assets::assets_dir().display()
+            ));
+        }
 
-            // Ctrl+1-9: Quick navigation to tabs
-            if i.modifiers.command {
-                if i.key_pressed(egui::Key::Num1) {
-                    self.selected_tab = Tab::Rendering;
-                } else if i.key_pressed(egui::Key::Num2) {
-                    self.selected_tab = Tab::Compute;
-                } else if i.key_pressed(egui::Key::Num3) {
-                    self.selected_tab = Tab::BufferConfig;
-                } else if i.key_pressed(egui::Key::Num4) {
-                    self.selected_tab = Tab::TextureConfig;
-                } else if i.key_pressed(egui::Key::Num5) {
-                    self.selected_tab = Tab::Console;
-                } else if i.key_pressed(egui::Key::Num6) {
-                    self.selected_tab = Tab::Settings;
-                }
+        // Files dropped on the window go to the panel for their type
+        let (dropped, now) = ctx.input(|i| (i.raw.dropped_files.clone(), i.time));
+        for file in &dropped {
+            self.open_dropped_file(file, now);
+        }
+
+        // Images on the clipboard are pasted into the Textures tab
+        #[cfg(not(target_arch = "wasm32"))]
+        if std::mem::take(&mut self.clipboard_paste_requested)
+            && self.selected_tab == Tab::TextureConfig
+        {
+            self.paste_clipboard_image(&ctx, now);
+        }
+
+        // Keyboard shortcuts, remappable in the Keyboard Shortcuts tab
+        // Text fields handle their own undo, so history shortcuts only apply
+        // when no text field has focus. Nothing runs while the tab records a
+        // new shortcut.
+        let text_focused = ctx.egui_wants_keyboard_input();
+        let actions = if self.keybindings_panel.is_capturing() {
+            Vec::new()
+        } else {
+            ctx.input(|i| self.keybindings_panel.bindings().triggered(i))
+        };
+        for action in actions {
+            if !(text_focused && action.yields_to_text_input()) {
+                self.run_action(action, device, queue);
             }
-        });
+        }
+        if let Some(command) = self.command_palette.ui(&ctx) {
+            self.run_command(command, device, queue);
+        }
 
         // Menu bar at the top
         ui.group(|ui| {
@@ -238,10 +456,77 @@ impl PlaygroundApp {
                 ui.colored_label(backend_color, backend_label)
                     .on_hover_text(current_backend.description());
 
+                if let Some(warning) = self.memory_budget.warning() {
+                    let color = if self.memory_budget.level() == BudgetLevel::Exceeded {
+                        egui::Color32::RED
+                    } else {
+                        egui::Color32::YELLOW
+                    };
+                    ui.add_space(10.0);
+                    if ui
+                        .button(egui::RichText::new("⚠ GPU memory").color(color))
+                        .on_hover_text(warning)
+                        .clicked()
+                    {
+                        self.selected_tab = Tab::ResourceInspector;
+                        self.open_section_for_tab(Tab::ResourceInspector);
+                    }
+                }
+
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let shortcut = self
+                        .keybindings_panel
+                        .bindings()
+                        .label(Action::CommandPalette)
+                        .unwrap_or_else(|| tr("keybindings-none"));
+                    if ui
+                        .button(tr("command-palette-button"))
+                        .on_hover_text(tr_args(
+                            "command-palette-button-tooltip",
+                            &[("shortcut", &shortcut)],
+                        ))
+                        .clicked()
+                    {
+                        self.toggle_command_palette();
+                    }
+                    ui.separator();
+
+                    // Undo/redo
+                    let redo_hint = match self.workspace().history.redo_label() {
+                        Some(label) => format!("Redo {} (Ctrl+Y)", label),
+                        None => "Nothing to redo".to_string(),
+                    };
+                    if ui
+                        .add_enabled(self.workspace().history.can_redo(), egui::Button::new("↷"))
+                        .on_hover_text(redo_hint)
+                        .clicked()
+                    {
+                        self.workspace_mut().redo();
+                    }
+                    let undo_hint = match self.workspace().history.undo_label() {
+                        Some(label) => format!("Undo {} (Ctrl+Z)", label),
+                        None => "Nothing to undo".to_string(),
+                    };
+                    if ui
+                        .add_enabled(self.workspace().history.can_undo(), egui::Button::new("↶"))
+                        .on_hover_text(undo_hint)
+                        .clicked()
+                    {
+                        self.workspace_mut().undo();
+                    }
+                    ui.separator();
+
                     // File operations
                     ui.label("File:");
 
+                    if ui
+                        .button("🆕 New Project")
+                        .on_hover_text("Start a project from a template (Ctrl+N)")
+                        .clicked()
+                    {
+                        self.open_new_project_dialog();
+                    }
+
                     if ui
                         .button("📦 Export Project")
                         .on_hover_text(
@@ -250,10 +535,30 @@ impl PlaygroundApp {
                         .clicked()
                     {
                         let state = self.export_state();
-                        self.rendering_panel
+                        self.workspace_mut()
+                            .rendering_panel
                             .export_to_standalone_project_with_state(&state);
                     }
 
+                    if ui
+                        .button("🧮 Export Compute Project")
+                        .on_hover_text(
+                            "Export the compute shader as a standalone project that runs the \
+                             dispatch on the configured buffer and prints the results",
+                        )
+                        .clicked()
+                    {
+                        let state = self.export_state();
+                        let workspace = self.workspace_mut();
+                        let shader_source = workspace.compute_pipeline_panel.shader_source.clone();
+                        let workgroups = workspace.compute_dispatch_panel.workgroups();
+                        workspace.rendering_panel.export_compute_project(
+                            &state,
+                            &shader_source,
+                            workgroups,
+                        );
+                    }
+
                     if ui
                         .button("💾 Save State")
                         .on_hover_text("Save current playground state to a file (Ctrl+S)")
@@ -352,6 +657,90 @@ impl PlaygroundApp {
                     ctx.copy_text(self.share_url.clone());
                     self.share_message = Some("✓ Copied to clipboard!".to_string());
                 }
+
+                if ui
+                    .button("🧬 Copy Permalink")
+                    .on_hover_text(
+                        "Copy the shader and pipeline setup as compact text to paste into a chat or issue",
+                    )
+                    .clicked()
+                {
+                    match permalink::encode(&self.export_state()) {
+                        Ok(link) => {
+                            self.share_message = Some(format!(
+                                "✓ Permalink copied to clipboard ({} characters)",
+                                link.len()
+                            ));
+                            ctx.copy_text(link.clone());
+                            self.share_url = link;
+                        }
+                        Err(e) => {
+                            self.share_message =
+                                Some(format!("✗ Failed to create permalink: {}", e));
+                        }
+                    }
+                }
+                if ui
+                    .button("📥 Import Permalink")
+                    .on_hover_text("Paste a permalink to load its shader and pipeline setup")
+                    .clicked()
+                {
+                    self.permalink_input = Some(String::new());
+                }
+
+                ui.separator();
+                ui.label("Screenshot:");
+                let shortcut = self
+                    .keybindings_panel
+                    .bindings()
+                    .label(Action::Screenshot)
+                    .map(|label| format!(" ({})", label))
+                    .unwrap_or_default();
+                if ui
+                    .button("📸 Capture")
+                    .on_hover_text(format!(
+                        "Save the window to the screenshot folder and copy it to the clipboard{}",
+                        shortcut
+                    ))
+                    .clicked()
+                {
+                    self.screenshot_requested = true;
+                }
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.screenshot_dir)
+                        .desired_width(160.0)
+                        .hint_text("screenshots"),
+                )
+                .on_hover_text("Folder where screenshots are saved");
+
+                ui.separator();
+                ui.label("RenderDoc:");
+                let response = ui.add_enabled(
+                    self.renderdoc.is_available() && !self.renderdoc.is_pending(),
+                    egui::Button::new("🎯 Capture Next Frame"),
+                );
+                let response = match self.renderdoc.load_error() {
+                    Some(error) => response.on_disabled_hover_text(error.to_string()),
+                    None => response.on_hover_text(
+                        "Capture the next frame in RenderDoc, tagged with the current panel and pipeline",
+                    ),
+                };
+                if response.clicked() {
+                    let label = self.capture_label();
+                    if let Err(e) = self.renderdoc.request_capture(label) {
+                        self.console_panel.error(e.to_string());
+                    }
+                }
+                if !self.renderdoc.captures().is_empty()
+                    && ui
+                        .button("🔍 Open RenderDoc")
+                        .on_hover_text("Open the replay UI to inspect the captures")
+                        .clicked()
+                {
+                    if let Err(e) = self.renderdoc.launch_replay_ui() {
+                        self.console_panel.error(e.to_string());
+                    }
+                }
             });
 
             // Show share message if any
@@ -379,12 +768,18 @@ impl PlaygroundApp {
             }
         });
 
+        ui.add_space(4.0);
+        self.workspace_tabs_ui(ui, device, queue);
+
         // Sidebar on the left
         ui.add_space(8.0);
-        ui.columns(2, |columns| {
+        // The lesson in progress gets a third column next to the panels
+        let lesson_open = self.lesson_panel.current().is_some();
+        let mut lesson_action = None;
+        ui.columns(if lesson_open { 3 } else { 2 }, |columns| {
             let ui = &mut columns[0];
             egui::ScrollArea::vertical().show(ui, |ui| {
-                ui.heading("Navigation");
+                ui.heading(tr("nav-heading"));
 
                 // Platform-appropriate shortcut hint
                 let shortcut_modifier = if cfg!(target_os = "macos") {
@@ -393,10 +788,10 @@ impl PlaygroundApp {
                     "Ctrl"
                 };
 
-                ui.label(format!("Quick navigation: {shortcut_modifier}+1-6"))
-                    .on_hover_text(format!(
-                        "Keyboard shortcuts:\n{0}+1: Rendering\n{0}+2: Compute\n{0}+3: Buffers\n{0}+4: Textures\n{0}+5: Console\n{0}+6: Settings",
-                        shortcut_modifier
+                ui.label(tr_args("nav-quick", &[("modifier", &shortcut_modifier)]))
+                    .on_hover_text(tr_args(
+                        "nav-quick-shortcuts",
+                        &[("modifier", &shortcut_modifier)],
                     ));
                 ui.separator();
                 ui.add_space(5.0);
@@ -404,8 +799,8 @@ impl PlaygroundApp {
                 // Setup Section
                 ui.push_id("setup_section", |ui| {
                     let header_response =
-                        ui.selectable_label(self.setup_section_open, "⚙️ Setup & Configuration")
-                            .on_hover_text("Configure GPU adapter and device settings");
+                        ui.selectable_label(self.setup_section_open, tr("nav-setup"))
+                            .on_hover_text(tr("nav-setup.tooltip"));
                     if header_response.clicked() {
                         self.setup_section_open = !self.setup_section_open;
                     }
@@ -416,18 +811,23 @@ impl PlaygroundApp {
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::AdapterSelection,
-                            "  Adapter Selection",
-                        ).on_hover_text("Choose and configure GPU adapters");
+                            format!("  {}", tr("nav-adapter-selection")),
+                        ).on_hover_text(tr("nav-adapter-selection.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::DeviceConfig,
-                            "  Device Config",
-                        ).on_hover_text("Enable/disable WebGPU features and adjust device limits");
+                            format!("  {}", tr("nav-device-config")),
+                        ).on_hover_text(tr("nav-device-config.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::SurfaceConfig,
+                            format!("  {}", tr("nav-surface-config")),
+                        ).on_hover_text(tr("nav-surface-config.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::DeviceInfo,
-                            "  Device Info",
-                        ).on_hover_text("View comprehensive GPU adapter information");
+                            format!("  {}", tr("nav-device-info")),
+                        ).on_hover_text(tr("nav-device-info.tooltip"));
                     });
                 }
                 ui.add_space(3.0);
@@ -435,8 +835,8 @@ impl PlaygroundApp {
                 // Rendering Section
                 ui.push_id("rendering_section", |ui| {
                     let header_response =
-                        ui.selectable_label(self.rendering_section_open, "🎨 Rendering & Graphics")
-                            .on_hover_text("Graphics rendering examples and pipeline configuration (Ctrl+1)");
+                        ui.selectable_label(self.rendering_section_open, tr("nav-rendering"))
+                            .on_hover_text(tr("nav-rendering.tooltip"));
                     if header_response.clicked() {
                         self.rendering_section_open = !self.rendering_section_open;
                     }
@@ -447,23 +847,33 @@ impl PlaygroundApp {
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::Rendering,
-                            "  Examples & Preview",
-                        ).on_hover_text("View rendering examples and live preview");
+                            format!("  {}", tr("nav-examples")),
+                        ).on_hover_text(tr("nav-examples.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::RenderPipelineConfig,
-                            "  Render Pipeline",
-                        ).on_hover_text("Configure render pipeline and shaders");
+                            format!("  {}", tr("nav-render-pipeline")),
+                        ).on_hover_text(tr("nav-render-pipeline.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::RenderPassConfig,
-                            "  Render Pass",
-                        ).on_hover_text("Configure render pass settings");
+                            format!("  {}", tr("nav-render-pass")),
+                        ).on_hover_text(tr("nav-render-pass.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::RenderTargets,
+                            format!("  {}", tr("nav-render-targets")),
+                        ).on_hover_text(tr("nav-render-targets.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::DrawCommand,
-                            "  Draw Commands",
-                        ).on_hover_text("Configure and execute draw commands");
+                            format!("  {}", tr("nav-draw-commands")),
+                        ).on_hover_text(tr("nav-draw-commands.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::FragmentPlayground,
+                            format!("  {}", tr("nav-fragment-playground")),
+                        ).on_hover_text(tr("nav-fragment-playground.tooltip"));
                     });
                 }
                 ui.add_space(3.0);
@@ -471,8 +881,8 @@ impl PlaygroundApp {
                 // Compute Section
                 ui.push_id("compute_section", |ui| {
                     let header_response =
-                        ui.selectable_label(self.compute_section_open, "🧮 Compute & ML")
-                            .on_hover_text("Compute shader operations and ML inferencing (Ctrl+2)");
+                        ui.selectable_label(self.compute_section_open, tr("nav-compute"))
+                            .on_hover_text(tr("nav-compute.tooltip"));
                     if header_response.clicked() {
                         self.compute_section_open = !self.compute_section_open;
                     }
@@ -483,18 +893,28 @@ impl PlaygroundApp {
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::Compute,
-                            "  Compute Panel",
-                        ).on_hover_text("Explore compute shader operations");
+                            format!("  {}", tr("nav-compute-panel")),
+                        ).on_hover_text(tr("nav-compute-panel.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::ComputePipelineConfig,
-                            "  Compute Pipeline",
-                        ).on_hover_text("Configure compute pipeline and shaders");
+                            format!("  {}", tr("nav-compute-pipeline")),
+                        ).on_hover_text(tr("nav-compute-pipeline.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::ComputeDispatch,
-                            "  Compute Dispatch",
-                        ).on_hover_text("Configure and dispatch compute operations");
+                            format!("  {}", tr("nav-compute-dispatch")),
+                        ).on_hover_text(tr("nav-compute-dispatch.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::AsyncCompute,
+                            format!("  {}", tr("nav-async-compute")),
+                        ).on_hover_text(tr("nav-async-compute.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::WorkgroupTuner,
+                            format!("  {}", tr("nav-workgroup-tuner")),
+                        ).on_hover_text(tr("nav-workgroup-tuner.tooltip"));
                     });
                 }
                 ui.add_space(3.0);
@@ -502,8 +922,8 @@ impl PlaygroundApp {
                 // Resources Section
                 ui.push_id("resources_section", |ui| {
                     let header_response =
-                        ui.selectable_label(self.resources_section_open, "📦 Resources")
-                            .on_hover_text("Manage GPU resources (buffers, textures, samplers)");
+                        ui.selectable_label(self.resources_section_open, tr("nav-resources"))
+                            .on_hover_text(tr("nav-resources.tooltip"));
                     if header_response.clicked() {
                         self.resources_section_open = !self.resources_section_open;
                     }
@@ -511,33 +931,33 @@ impl PlaygroundApp {
 
                 if self.resources_section_open {
                     ui.indent("resources_indent", |ui| {
-                        ui.selectable_value(&mut self.selected_tab, Tab::BufferConfig, "  Buffers")
-                            .on_hover_text("Create and configure GPU buffers (Ctrl+3)");
+                        ui.selectable_value(&mut self.selected_tab, Tab::BufferConfig, format!("  {}", tr("nav-buffers")))
+                            .on_hover_text(tr("nav-buffers.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::TextureConfig,
-                            "  Textures",
-                        ).on_hover_text("Load and configure textures from images (Ctrl+4)");
+                            format!("  {}", tr("nav-textures")),
+                        ).on_hover_text(tr("nav-textures.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::SamplerConfig,
-                            "  Samplers",
-                        ).on_hover_text("Configure texture sampling modes");
+                            format!("  {}", tr("nav-samplers")),
+                        ).on_hover_text(tr("nav-samplers.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::ModelLoader,
-                            "  3D Models",
-                        ).on_hover_text("Load and view 3D models");
+                            format!("  {}", tr("nav-models")),
+                        ).on_hover_text(tr("nav-models.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::BindGroupConfig,
-                            "  Bind Groups",
-                        ).on_hover_text("Create bind groups for shader resources");
+                            format!("  {}", tr("nav-bind-groups")),
+                        ).on_hover_text(tr("nav-bind-groups.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::BindGroupLayoutConfig,
-                            "  Bind Group Layouts",
-                        ).on_hover_text("Define bind group layouts");
+                            format!("  {}", tr("nav-bind-group-layouts")),
+                        ).on_hover_text(tr("nav-bind-group-layouts.tooltip"));
                     });
                 }
                 ui.add_space(3.0);
@@ -545,8 +965,8 @@ impl PlaygroundApp {
                 // Tools Section
                 ui.push_id("tools_section", |ui| {
                     let header_response =
-                        ui.selectable_label(self.tools_section_open, "🔧 Tools & Debugging")
-                            .on_hover_text("Debugging tools, console, and performance monitoring");
+                        ui.selectable_label(self.tools_section_open, tr("nav-tools"))
+                            .on_hover_text(tr("nav-tools.tooltip"));
                     if header_response.clicked() {
                         self.tools_section_open = !self.tools_section_open;
                     }
@@ -557,62 +977,112 @@ impl PlaygroundApp {
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::Tutorials,
-                            "  Tutorials",
-                        ).on_hover_text("Interactive guided tutorials for learning WebGPU");
+                            format!("  {}", tr("nav-tutorials")),
+                        ).on_hover_text(tr("nav-tutorials.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::LearningPath,
-                            "  Learning Path",
-                        ).on_hover_text("Visual learning path showing WebGPU concepts and progress");
+                            format!("  {}", tr("nav-learning-path")),
+                        ).on_hover_text(tr("nav-learning-path.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::Presets,
-                            "  Configuration Presets",
-                        ).on_hover_text("Browse and load preset configurations for common rendering scenarios");
+                            format!("  {}", tr("nav-presets")),
+                        ).on_hover_text(tr("nav-presets.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::History,
+                            format!("  {}", tr("nav-history")),
+                        ).on_hover_text(tr("nav-history.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::ResourceInspector,
-                            "  Resource Inspector",
-                        ).on_hover_text("Inspect all GPU resources");
+                            format!("  {}", tr("nav-resource-inspector")),
+                        ).on_hover_text(tr("nav-resource-inspector.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::BufferInspector,
-                            "  Buffer Inspector",
-                        ).on_hover_text("View and inspect buffer contents");
+                            format!("  {}", tr("nav-buffer-inspector")),
+                        ).on_hover_text(tr("nav-buffer-inspector.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::TextureInspector,
-                            "  Texture Inspector",
-                        ).on_hover_text("View and inspect texture data");
+                            format!("  {}", tr("nav-texture-inspector")),
+                        ).on_hover_text(tr("nav-texture-inspector.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::PipelineDebugger,
-                            "  Pipeline Debugger",
-                        ).on_hover_text("Debug render and compute pipelines");
+                            format!("  {}", tr("nav-pipeline-debugger")),
+                        ).on_hover_text(tr("nav-pipeline-debugger.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::DebugCounters,
+                            format!("  {}", tr("nav-debug-counters")),
+                        ).on_hover_text(tr("nav-debug-counters.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::ShaderVariants,
+                            format!("  {}", tr("nav-shader-variants")),
+                        ).on_hover_text(tr("nav-shader-variants.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::ShaderTranslation,
+                            format!("  {}", tr("nav-shader-translation")),
+                        ).on_hover_text(tr("nav-shader-translation.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::ExternalComparison,
+                            format!("  {}", tr("nav-external-comparison")),
+                        ).on_hover_text(tr("nav-external-comparison.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::ExerciseChecks,
+                            format!("  {}", tr("nav-exercise-checks")),
+                        ).on_hover_text(tr("nav-exercise-checks.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::CommandRecording,
-                            "  Command Recording",
-                        ).on_hover_text("Record and replay GPU commands");
+                            format!("  {}", tr("nav-command-recording")),
+                        ).on_hover_text(tr("nav-command-recording.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::ApiCoverage,
-                            "  API Coverage",
-                        ).on_hover_text("Track and visualize WebGPU API usage");
+                            format!("  {}", tr("nav-api-coverage")),
+                        ).on_hover_text(tr("nav-api-coverage.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::ApiReference,
-                            "  API Reference",
-                        ).on_hover_text("Browse WebGPU API documentation and examples");
-                        ui.selectable_value(&mut self.selected_tab, Tab::Console, "  Console")
-                            .on_hover_text("View GPU errors and validation messages (Ctrl+5)");
+                            format!("  {}", tr("nav-api-reference")),
+                        ).on_hover_text(tr("nav-api-reference.tooltip"));
+                        ui.selectable_value(&mut self.selected_tab, Tab::Console, format!("  {}", tr("nav-console")))
+                            .on_hover_text(tr("nav-console.tooltip"));
                         ui.selectable_value(
                             &mut self.selected_tab,
                             Tab::Performance,
-                            "  Performance",
-                        ).on_hover_text("Monitor GPU performance metrics");
-                        ui.selectable_value(&mut self.selected_tab, Tab::Settings, "  Settings")
-                            .on_hover_text("Application settings and preferences (Ctrl+6)");
+                            format!("  {}", tr("nav-performance")),
+                        ).on_hover_text(tr("nav-performance.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::FramePacing,
+                            format!("  {}", tr("nav-frame-pacing")),
+                        ).on_hover_text(tr("nav-frame-pacing.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::CpuProfiler,
+                            format!("  {}", tr("nav-cpu-profiler")),
+                        ).on_hover_text(tr("nav-cpu-profiler.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::PuffinViewer,
+                            format!("  {}", tr("nav-puffin-viewer")),
+                        ).on_hover_text(tr("nav-puffin-viewer.tooltip"));
+                        ui.selectable_value(
+                            &mut self.selected_tab,
+                            Tab::KeyBindings,
+                            format!("  {}", tr("nav-keybindings")),
+                        ).on_hover_text(tr("nav-keybindings.tooltip"));
+                        ui.selectable_value(&mut self.selected_tab, Tab::Settings, format!("  {}", tr("nav-settings")))
+                            .on_hover_text(tr("nav-settings.tooltip"));
                     });
                 }
             });
@@ -624,47 +1094,113 @@ impl PlaygroundApp {
             self.learning_path_panel
                 .update_from_tutorial_state(&tutorial_state.completed_tutorials);
 
+            let workspace = &mut self.workspaces[self.active_workspace];
             match self.selected_tab {
                 Tab::AdapterSelection => self.adapter_selection.ui(ui),
                 Tab::DeviceConfig => self.device_config.ui(ui),
-                Tab::DeviceInfo => self.device_info.ui(ui),
-                Tab::Rendering => self.rendering_panel.ui(ui, device, queue, renderer),
+                Tab::SurfaceConfig => self.surface_config_panel.ui(ui),
+                Tab::DeviceInfo => {
+                    ui.horizontal(|ui| {
+                        ui.label("Debug:");
+                        if ui
+                            .button("💥 Simulate Device Loss")
+                            .on_hover_text("Destroy the GPU device to test recovery; a new device is requested right away")
+                            .clicked()
+                        {
+                            self.device_loss_requested = true;
+                        }
+                    });
+                    ui.separator();
+                    if self.device_info.virtual_device().is_some() {
+                        self.device_info
+                            .set_project_descriptors(workspace.project_descriptors());
+                    }
+                    self.device_info.ui_with_probes(ui, Some((device, queue)))
+                }
+                Tab::Rendering => {
+                    workspace.rendering_panel
+                        .set_lint_device(self.device_info.virtual_device());
+                    workspace.rendering_panel.set_processing_image(
+                        workspace.texture_panel.get_loaded_texture_data().map(Vec::as_slice),
+                    );
+                    workspace.rendering_panel.ui(ui, device, queue, renderer)
+                }
                 Tab::BufferConfig => {
-                    self.buffer_panel
+                    workspace.buffer_panel
                         .ui_with_preview(ui, Some(device), Some(queue), Some(renderer))
                 }
-                Tab::SamplerConfig => self.sampler_panel.ui(ui),
-                Tab::TextureConfig => self.texture_panel.ui_with_preview(
+                Tab::SamplerConfig => workspace
+                    .sampler_panel
+                    .ui_with_preview(ui, device, queue, renderer),
+                Tab::TextureConfig => workspace.texture_panel.ui_with_preview(
                     ui,
                     Some(device),
                     Some(queue),
                     Some(renderer),
                 ),
-                Tab::ModelLoader => self.model_loader_panel.show(ui, device),
-                Tab::BindGroupConfig => self.bind_group_panel.ui(ui),
-                Tab::BindGroupLayoutConfig => self.bind_group_layout_panel.ui(ui),
+                Tab::ModelLoader => workspace
+                    .model_loader_panel
+                    .ui_with_preview(ui, device, queue, renderer),
+                Tab::BindGroupConfig => {
+                    workspace
+                        .bind_group_panel
+                        .set_baked_texture(workspace.texture_panel.baked_texture());
+                    workspace.bind_group_panel.ui_with_device(ui, Some(device))
+                }
+                Tab::BindGroupLayoutConfig => workspace.bind_group_layout_panel.ui(ui),
                 Tab::ComputePipelineConfig => {
-                    self.compute_pipeline_panel.ui_with_device(ui, Some(device))
+                    workspace.compute_pipeline_panel.ui_with_device(ui, Some(device))
                 }
-                Tab::RenderPipelineConfig => self.render_pipeline_panel.ui_with_preview(
+                Tab::RenderPipelineConfig => workspace.render_pipeline_panel.ui_with_preview(
                     ui,
                     Some(device),
                     Some(queue),
                     Some(renderer),
                 ),
-                Tab::DrawCommand => self.draw_command_panel.ui(ui),
-                Tab::RenderPassConfig => self.render_pass_panel.ui(ui),
-                Tab::ComputeDispatch => self.compute_dispatch_panel.ui(ui),
+                Tab::DrawCommand => workspace.draw_command_panel.ui(ui),
+                Tab::RenderPassConfig => workspace.render_pass_panel.ui(ui),
+                Tab::RenderTargets => workspace.render_targets_panel.ui(ui, device, queue),
+                Tab::FragmentPlayground => workspace
+                    .fragment_playground
+                    .ui_with_preview(ui, device, queue, renderer),
+                Tab::ComputeDispatch => workspace.compute_dispatch_panel.ui(ui),
+                Tab::AsyncCompute => workspace.async_compute_panel.ui(ui, device, queue),
+                Tab::WorkgroupTuner => workspace.workgroup_tuner_panel.ui(ui, device, queue),
                 Tab::Compute => self
                     .compute_panel
                     .ui_with_device(ui, Some(device), Some(queue)),
                 Tab::Console => self.console_panel.ui(ui),
-                Tab::ResourceInspector => self.resource_inspector_panel.ui(ui),
-                Tab::BufferInspector => self.buffer_inspector.ui(ui),
-                Tab::TextureInspector => self.texture_inspector.ui(ui),
-                Tab::PipelineDebugger => self.pipeline_debugger.ui(ui),
+                Tab::ResourceInspector => workspace
+                    .resource_inspector_panel
+                    .ui_with_budget(ui, &mut self.memory_budget),
+                Tab::BufferInspector => workspace.buffer_inspector.ui_with_image(
+                    ui,
+                    Some(device),
+                    Some(queue),
+                    Some(renderer),
+                ),
+                Tab::TextureInspector => workspace.texture_inspector.ui(ui),
+                Tab::PipelineDebugger => workspace.pipeline_debugger.ui(ui),
+                Tab::DebugCounters => workspace.debug_counters_panel.ui(ui, device, queue),
+                Tab::ShaderVariants => workspace.shader_variants_panel.ui(ui, Some(device)),
+                Tab::ShaderTranslation => {
+                    workspace.shader_translation_panel
+                        .sync_source(workspace.rendering_panel.shader_source());
+                    workspace.shader_translation_panel.ui(ui)
+                }
+                Tab::ExternalComparison => workspace.external_comparison_panel.ui(ui),
+                Tab::ExerciseChecks => {
+                    let live = workspace.export_state();
+                    self.exercise_panel.ui(ui, &live);
+                }
                 Tab::Performance => self.performance_panel.ui(ui),
-                Tab::CommandRecording => self.command_recording_panel.ui(ui),
+                Tab::FramePacing => self.frame_pacing_panel.ui(ui),
+                Tab::CpuProfiler => self
+                    .cpu_profiler_panel
+                    .ui(ui, CpuProfiler::global()),
+                Tab::PuffinViewer => self.puffin_viewer_panel.ui(ui),
+                Tab::KeyBindings => self.keybindings_panel.ui(ui),
+                Tab::CommandRecording => workspace.command_recording_panel.ui(ui),
                 Tab::ApiCoverage => {
                     let tracker = ApiCoverageTracker::global();
                     if let Some(nav_request) = self.api_coverage_panel.ui(ui, tracker) {
@@ -675,8 +1211,20 @@ impl PlaygroundApp {
                     }
                 }
                 Tab::ApiReference => self.api_reference_panel.ui(ui),
-                Tab::Tutorials => self.tutorial_panel.ui(ui),
+                Tab::Tutorials => {
+                    if let Some(action) = self.lesson_panel.catalog_ui(ui) {
+                        lesson_action = Some(action);
+                    }
+                    ui.separator();
+                    self.tutorial_panel.ui(ui);
+                }
                 Tab::LearningPath => self.learning_path_panel.ui(ui),
+                Tab::History => {
+                    let now = ctx.input(|i| i.time);
+                    if let Some(state) = workspace.history_panel.ui(ui, &mut workspace.history, now) {
+                        workspace.restore_history_state(state);
+                    }
+                }
                 Tab::Presets => {
                     // Handle preset loading
                     if let Some(preset_state) = self.preset_panel.ui(ui) {
@@ -685,23 +1233,442 @@ impl PlaygroundApp {
                     }
                 }
                 Tab::Settings => {
-                    if let Some(new_theme) = self.settings_panel.ui(ui) {
-                        // Apply the theme change
-                        Self::apply_theme(&ctx, new_theme);
-                        // Save the state with the new theme
+                    if self.settings_panel.ui(ui).is_some() {
+                        // Apply the theme change, including to the previews
+                        self.apply_theme(&ctx);
+                        self.apply_theme_to_previews();
+                        self.theme_save_pending = true;
+                    }
+                    // Save the theme once a color drag in the custom theme
+                    // editor is over, not on every frame of it
+                    if self.theme_save_pending && !ctx.input(|i| i.pointer.any_down()) {
+                        self.theme_save_pending = false;
                         let filename = self.save_load_filename.clone();
                         let path = std::path::Path::new(&filename);
                         if let Err(e) = self.save_state_to_file(path) {
                             log::warn!("Failed to save theme preference: {}", e);
                         }
                     }
+                    if self.settings_panel.take_language_changed() {
+                        // The new language shows from the next frame; save it
+                        let filename = self.save_load_filename.clone();
+                        let path = std::path::Path::new(&filename);
+                        if let Err(e) = self.save_state_to_file(path) {
+                            log::warn!("Failed to save language preference: {}", e);
+                        }
+                    }
                 }
             }
             });
+
+            if let Some(ui) = columns.get_mut(2) {
+                let live = self.workspace().export_state();
+                egui::ScrollArea::vertical()
+                    .id_salt("lesson_side_panel")
+                    .show(ui, |ui| {
+                        if let Some(action) = self.lesson_panel.side_panel_ui(ui, &live) {
+                            lesson_action = Some(action);
+                        }
+                    });
+            }
         });
+        if let Some(action) = lesson_action {
+            self.apply_lesson_action(action);
+        }
 
         // Track panel visits for tutorial system
         self.track_panel_visit(self.selected_tab);
+
+        // Record edits for undo/redo once an interaction has finished
+        if ctx.input(ends_interaction) {
+            self.workspace_mut().history.mark_edited();
+        }
+        if self.workspace_mut().history.take_edited() {
+            let state = self.workspace().export_state();
+            self.workspace_mut()
+                .history
+                .record(&state, ctx.input(|i| i.time));
+        }
+
+        self.show_toast(&ctx);
+        self.show_permalink_dialog(&ctx);
+        self.show_new_project_dialog(&ctx, device, queue);
+        Self::show_background_jobs(&ctx);
+        drop_router::overlay_ui(&ctx);
+    }
+
+    /// Tab strip for switching, creating, renaming and closing workspaces
+    fn workspace_tabs_ui(&mut self, ui: &mut egui::Ui, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let mut action = None;
+        let can_close = self.workspaces.len() > 1;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Workspaces:");
+            for (index, workspace) in self.workspaces.iter_mut().enumerate() {
+                if self.renaming_workspace == Some(index) {
+                    let response = ui
+                        .add(egui::TextEdit::singleline(&mut workspace.name).desired_width(120.0));
+                    response.request_focus();
+                    if response.lost_focus() {
+                        if workspace.name.trim().is_empty() {
+                            workspace.name = format!("Workspace {}", index + 1);
+                        }
+                        self.renaming_workspace = None;
+                    }
+                    continue;
+                }
+
+                let response = ui
+                    .selectable_label(index == self.active_workspace, &workspace.name)
+                    .on_hover_text("Click to switch, double-click to rename, right-click for more");
+                if response.clicked() {
+                    action = Some(WorkspaceAction::Switch(index));
+                }
+                if response.double_clicked() {
+                    self.renaming_workspace = Some(index);
+                }
+                response.context_menu(|ui| {
+                    if ui.button("✏ Rename").clicked() {
+                        self.renaming_workspace = Some(index);
+                        ui.close();
+                    }
+                    if ui.button("⧉ Duplicate").clicked() {
+                        action = Some(WorkspaceAction::Duplicate(index));
+                        ui.close();
+                    }
+                    if ui
+                        .add_enabled(can_close, egui::Button::new("✕ Close"))
+                        .clicked()
+                    {
+                        action = Some(WorkspaceAction::Close(index));
+                        ui.close();
+                    }
+                });
+            }
+
+            if ui
+                .button("➕")
+                .on_hover_text("New workspace with default settings")
+                .clicked()
+            {
+                action = Some(WorkspaceAction::New);
+            }
+            if ui
+                .button("⧉")
+                .on_hover_text("Duplicate the current workspace")
+                .clicked()
+            {
+                action = Some(WorkspaceAction::Duplicate(self.active_workspace));
+            }
+        });
+
+        match action {
+            Some(WorkspaceAction::Switch(index)) => self.active_workspace = index,
+            Some(WorkspaceAction::New) => {
+                let name = next_workspace_name(self.workspaces.iter().map(|w| w.name.as_str()));
+                self.workspaces.push(Workspace::new(name, device, queue));
+                self.active_workspace = self.workspaces.len() - 1;
+            }
+            Some(WorkspaceAction::Duplicate(index)) => {
+                let source = &self.workspaces[index];
+                let state = source.export_state();
+                let mut workspace =
+                    Workspace::new(format!("{} (copy)", source.name), device, queue);
+                workspace.import_state(&state);
+                let state = workspace.export_state();
+                workspace.history.reset(&state, ui.input(|i| i.time));
+                self.workspaces.insert(index + 1, workspace);
+                self.active_workspace = index + 1;
+            }
+            Some(WorkspaceAction::Close(index)) if self.workspaces.len() > 1 => {
+                // Dropping the workspace releases its GPU resources
+                self.workspaces.remove(index);
+                if self.active_workspace > index || self.active_workspace == self.workspaces.len() {
+                    self.active_workspace -= 1;
+                }
+                self.renaming_workspace = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Dialog for pasting a permalink, with a preview of what it contains
+    fn show_permalink_dialog(&mut self, ctx: &egui::Context) {
+        let Some(input) = &mut self.permalink_input else {
+            return;
+        };
+
+        let mut open = true;
+        let mut import = false;
+        let mut cancelled = false;
+        egui::Window::new("📥 Import Permalink")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label("Paste a permalink, or a link or message containing one:");
+                ui.add(
+                    egui::TextEdit::multiline(input)
+                        .desired_rows(4)
+                        .desired_width(f32::INFINITY)
+                        .hint_text(format!("{}…", permalink::PREFIX)),
+                );
+                ui.add_space(5.0);
+
+                let decoded = (!input.trim().is_empty()).then(|| permalink::decode(input));
+                match &decoded {
+                    Some(Ok(state)) => {
+                        ui.colored_label(egui::Color32::GREEN, "✓ Valid permalink");
+                        if let Some(shader) = &state.shader_editor {
+                            ui.label(format!(
+                                "Shader: {} ({} lines)",
+                                shader.label,
+                                shader.source_code.lines().count()
+                            ));
+                        }
+                        if let Some(pipeline) = &state.render_pipeline_panel {
+                            ui.label(format!("Render pipeline: {}", pipeline.label));
+                        }
+                        if let Some(pipeline) = &state.compute_pipeline_panel {
+                            ui.label(format!("Compute pipeline: {}", pipeline.label));
+                        }
+                        ui.weak(
+                            "Replaces the shader and pipelines of the current workspace; \
+                             undo reverts the import.",
+                        );
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::RED, format!("✗ {}", e));
+                    }
+                    None => {}
+                }
+
+                ui.horizontal(|ui| {
+                    import = ui
+                        .add_enabled(matches!(decoded, Some(Ok(_))), egui::Button::new("Import"))
+                        .clicked();
+                    cancelled = ui.button("Cancel").clicked();
+                });
+            });
+
+        if import {
+            let text = std::mem::take(input);
+            match self.import_permalink(&text) {
+                Ok(()) => {
+                    self.share_message = Some("✓ Imported permalink".to_string());
+                    self.permalink_input = None;
+                }
+                Err(e) => {
+                    self.share_message = Some(format!("✗ Failed to import permalink: {}", e));
+                }
+            }
+        } else if cancelled || !open {
+            self.permalink_input = None;
+        }
+    }
+
+    /// Load the shader and pipelines of a permalink into the active workspace
+    ///
+    /// The rest of the workspace and the local shader version history are
+    /// kept.
+    pub fn import_permalink(&mut self, text: &str) -> Result<(), PermalinkError> {
+        let mut state = permalink::decode(text)?;
+        if let (Some(shared), Some(local)) = (
+            &mut state.shader_editor,
+            self.workspace().export_state().shader_editor,
+        ) {
+            shared.history = local.history;
+        }
+        self.workspace_mut().import_state(&state);
+        log::info!("Playground setup imported from a permalink");
+        Ok(())
+    }
+
+    /// Open the New Project dialog on the blank template
+    fn open_new_project_dialog(&mut self) {
+        let name = next_workspace_name(self.workspaces.iter().map(|w| w.name.as_str()));
+        self.new_project = Some(NewProjectDialog {
+            template: ProjectTemplate::default(),
+            name,
+            new_workspace: true,
+        });
+    }
+
+    /// Dialog for picking the template and name of a new project
+    fn show_new_project_dialog(
+        &mut self,
+        ctx: &egui::Context,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        let Some(dialog) = &mut self.new_project else {
+            return;
+        };
+
+        let mut open = true;
+        let mut create = false;
+        let mut cancelled = false;
+        egui::Window::new("🆕 New Project")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(160.0);
+                        for template in ProjectTemplate::ALL {
+                            ui.selectable_value(&mut dialog.template, template, template.name());
+                        }
+                    });
+                    ui.separator();
+                    ui.vertical(|ui| {
+                        ui.strong(dialog.template.name());
+                        ui.label(dialog.template.description());
+                        ui.add_space(5.0);
+                        let state = dialog.template.state();
+                        let panels = [
+                            (state.shader_editor.is_some(), "Shader"),
+                            (state.buffer_panel.is_some(), "Buffer"),
+                            (state.texture_panel.is_some(), "Texture"),
+                            (state.sampler_panel.is_some(), "Sampler"),
+                            (state.bind_group_panel.is_some(), "Bind Group"),
+                            (state.render_pipeline_panel.is_some(), "Render Pipeline"),
+                            (state.compute_pipeline_panel.is_some(), "Compute Pipeline"),
+                        ];
+                        let included: Vec<&str> = panels
+                            .iter()
+                            .filter(|(set, _)| *set)
+                            .map(|(_, panel)| *panel)
+                            .collect();
+                        ui.weak(format!("Sets up: {}", included.join(", ")));
+                    });
+                });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut dialog.name);
+                });
+                ui.checkbox(&mut dialog.new_workspace, "Open in a new workspace")
+                    .on_hover_text(
+                        "Otherwise the project replaces the active workspace, \
+                         including its undo history",
+                    );
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    create = ui
+                        .add_enabled(!dialog.name.trim().is_empty(), egui::Button::new("Create"))
+                        .clicked();
+                    cancelled = ui.button("Cancel").clicked();
+                });
+            });
+
+        if create {
+            if let Some(dialog) = self.new_project.take() {
+                self.create_project(dialog, ctx.input(|i| i.time), device, queue);
+            }
+        } else if cancelled || !open {
+            self.new_project = None;
+        }
+    }
+
+    /// Start a workspace from a template
+    ///
+    /// The workspace is built from scratch so that panels the template
+    /// doesn't set keep their defaults rather than the previous project's
+    /// values.
+    fn create_project(
+        &mut self,
+        dialog: NewProjectDialog,
+        now: f64,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        let state = dialog.template.state();
+        let mut workspace = Workspace::new(dialog.name.trim(), device, queue);
+        workspace.import_project_state(&state);
+        let applied = workspace.export_state();
+        workspace.history.reset(&applied, now);
+
+        if dialog.new_workspace {
+            self.workspaces.push(workspace);
+            self.active_workspace = self.workspaces.len() - 1;
+        } else {
+            // Dropping the replaced workspace releases its GPU resources
+            self.workspaces[self.active_workspace] = workspace;
+        }
+        self.renaming_workspace = None;
+
+        let message = format!(
+            "✓ Created {} from the {} template",
+            dialog.name.trim(),
+            dialog.template.name()
+        );
+        log::info!("{}", message);
+        self.toast = Some(Toast {
+            message,
+            success: true,
+            expires_at: now + TOAST_DURATION,
+        });
+    }
+
+    /// Render the current toast notification, if any
+    fn show_toast(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        if self.toast.as_ref().is_some_and(|t| now >= t.expires_at) {
+            self.toast = None;
+        }
+        let Some(toast) = &self.toast else {
+            return;
+        };
+
+        egui::Area::new(egui::Id::new("playground_toast"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -16.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.colored_label(
+                        if toast.success {
+                            egui::Color32::GREEN
+                        } else {
+                            egui::Color32::RED
+                        },
+                        &toast.message,
+                    );
+                });
+            });
+    }
+
+    /// List image decodes and model imports still running in the background
+    fn show_background_jobs(ctx: &egui::Context) {
+        let jobs = JobSystem::global().active();
+        if jobs.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("playground_background_jobs"))
+            .anchor(egui::Align2::LEFT_BOTTOM, [16.0, -16.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    for job in jobs {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(&job.label);
+                            let text = if job.running {
+                                job.stage.clone()
+                            } else {
+                                "Queued".to_string()
+                            };
+                            ui.add(
+                                egui::ProgressBar::new(job.fraction)
+                                    .desired_width(120.0)
+                                    .text(text),
+                            );
+                        });
+                    }
+                });
+            });
     }
 
     /// Track panel visits for tutorial system
@@ -726,6 +1693,152 @@ impl PlaygroundApp {
         }
     }
 
+    /// Carry out a request of the lesson panel
+    fn apply_lesson_action(&mut self, action: LessonAction) {
+        match action {
+            // Recorded in the undo history like any other edit
+            LessonAction::LoadState(state) => self.workspace_mut().import_project_state(&state),
+            LessonAction::OpenPanel(target) => {
+                self.selected_tab = Self::highlight_target_to_tab(target);
+                self.open_section_for_tab(self.selected_tab);
+            }
+        }
+    }
+
+    /// Tab showing the panel a tutorial or lesson points at
+    fn highlight_target_to_tab(target: wgpu_playground_core::tutorial::HighlightTarget) -> Tab {
+        use wgpu_playground_core::tutorial::HighlightTarget;
+
+        match target {
+            HighlightTarget::RenderPipeline => Tab::RenderPipelineConfig,
+            HighlightTarget::BufferConfig => Tab::BufferConfig,
+            HighlightTarget::TextureConfig => Tab::TextureConfig,
+            HighlightTarget::BindGroup => Tab::BindGroupConfig,
+            HighlightTarget::RenderPass => Tab::RenderPassConfig,
+            HighlightTarget::DrawCommand => Tab::DrawCommand,
+            HighlightTarget::ComputePipeline => Tab::ComputePipelineConfig,
+            HighlightTarget::ComputeDispatch => Tab::ComputeDispatch,
+            HighlightTarget::Rendering => Tab::Rendering,
+        }
+    }
+
+    /// Carry out an action triggered by its keyboard shortcut
+    fn run_action(&mut self, action: Action, device: &wgpu::Device, queue: &wgpu::Queue) {
+        match action {
+            Action::NewProject => self.open_new_project_dialog(),
+            Action::SaveState => {
+                let filename = self.save_load_filename.clone();
+                let path = std::path::Path::new(&filename);
+                if let Err(e) = self.save_state_to_file(path) {
+                    log::error!("Failed to save state: {}", e);
+                } else {
+                    log::info!("State saved to {}", filename);
+                }
+            }
+            Action::LoadState => {
+                let filename = self.save_load_filename.clone();
+                let path = std::path::Path::new(&filename);
+                if let Err(e) = self.load_state_from_file(path) {
+                    log::error!("Failed to load state: {}", e);
+                } else {
+                    log::info!("State loaded from {}", filename);
+                }
+            }
+            Action::Undo => self.workspace_mut().undo(),
+            Action::Redo => self.workspace_mut().redo(),
+            Action::CompileShader => {
+                self.selected_tab = Tab::Rendering;
+                self.workspace_mut().rendering_panel.compile_shader(device);
+            }
+            Action::RunDispatch => {
+                self.selected_tab = Tab::Compute;
+                self.workspace_mut().compute_panel.run(device, queue);
+            }
+            Action::Screenshot => self.screenshot_requested = true,
+            Action::CaptureFrame => {
+                if !self.renderdoc.is_pending() {
+                    let label = self.capture_label();
+                    if let Err(e) = self.renderdoc.request_capture(label) {
+                        self.console_panel.error(e.to_string());
+                    }
+                }
+            }
+            Action::TogglePreview => {
+                self.selected_tab = Tab::Rendering;
+                self.workspace_mut()
+                    .rendering_panel
+                    .toggle_preview(device, queue);
+            }
+            Action::CommandPalette => self.toggle_command_palette(),
+            Action::ShowRendering => self.selected_tab = Tab::Rendering,
+            Action::ShowCompute => self.selected_tab = Tab::Compute,
+            Action::ShowBuffers => self.selected_tab = Tab::BufferConfig,
+            Action::ShowTextures => self.selected_tab = Tab::TextureConfig,
+            Action::ShowConsole => self.selected_tab = Tab::Console,
+            Action::ShowSettings => self.selected_tab = Tab::Settings,
+        }
+    }
+
+    /// Open the command palette over the commands of every panel, or close
+    /// it if it is open
+    fn toggle_command_palette(&mut self) {
+        if self.command_palette.is_open() {
+            self.command_palette.close();
+        } else {
+            let registry = self.command_registry();
+            self.command_palette.open(registry);
+        }
+    }
+
+    /// Commands of the panels, built each time the palette opens so that
+    /// titles and shortcuts are current
+    fn command_registry(&self) -> CommandRegistry {
+        let mut registry = CommandRegistry::new();
+        self.keybindings_panel.register_commands(&mut registry);
+        let bindings = self.keybindings_panel.bindings();
+        for tab in Tab::ALL {
+            let panel = tr(tab.nav_key());
+            registry.register(
+                Command::new(
+                    CommandKind::OpenPanel(tab.nav_key()),
+                    tr_args("command-palette-open-panel", &[("panel", &panel)]),
+                )
+                .with_keywords([tr(&format!("{}.tooltip", tab.nav_key()))])
+                .with_shortcut(tab.show_action().and_then(|action| bindings.label(action))),
+            );
+        }
+        self.preset_panel.register_commands(&mut registry);
+        self.workspace()
+            .rendering_panel
+            .register_commands(&mut registry);
+        registry
+    }
+
+    /// Carry out a command chosen in the command palette
+    fn run_command(&mut self, command: CommandKind, device: &wgpu::Device, queue: &wgpu::Queue) {
+        match command {
+            CommandKind::Action(action) => self.run_action(action, device, queue),
+            CommandKind::OpenPanel(key) => {
+                if let Some(tab) = Tab::ALL.into_iter().find(|tab| tab.nav_key() == key) {
+                    self.selected_tab = tab;
+                    self.open_section_for_tab(tab);
+                }
+            }
+            CommandKind::ApplyPreset(id) => {
+                if let Some(preset) = get_preset(id) {
+                    self.load_state_from_preset(preset.state);
+                }
+            }
+            CommandKind::LoadExample(id) => {
+                self.selected_tab = Tab::Rendering;
+                self.open_section_for_tab(Tab::Rendering);
+                self.workspace_mut()
+                    .rendering_panel
+                    .load_example(id, device, queue);
+            }
+        }
+    }
+
     /// Convert a NavigationRequest from API Coverage panel to a Tab
     fn navigation_request_to_tab(request: NavigationRequest) -> Tab {
         match request {
@@ -746,16 +1859,22 @@ impl PlaygroundApp {
     /// Open the sidebar section that contains the given tab
     fn open_section_for_tab(&mut self, tab: Tab) {
         match tab {
-            Tab::AdapterSelection | Tab::DeviceConfig | Tab::DeviceInfo => {
+            Tab::AdapterSelection | Tab::DeviceConfig | Tab::SurfaceConfig | Tab::DeviceInfo => {
                 self.setup_section_open = true;
             }
             Tab::Rendering
             | Tab::RenderPipelineConfig
             | Tab::RenderPassConfig
-            | Tab::DrawCommand => {
+            | Tab::RenderTargets
+            | Tab::DrawCommand
+            | Tab::FragmentPlayground => {
                 self.rendering_section_open = true;
             }
-            Tab::Compute | Tab::ComputePipelineConfig | Tab::ComputeDispatch => {
+            Tab::Compute
+            | Tab::ComputePipelineConfig
+            | Tab::ComputeDispatch
+            | Tab::AsyncCompute
+            | Tab::WorkgroupTuner => {
                 self.compute_section_open = true;
             }
             Tab::BufferConfig
@@ -771,28 +1890,41 @@ impl PlaygroundApp {
             | Tab::BufferInspector
             | Tab::TextureInspector
             | Tab::PipelineDebugger
+            | Tab::DebugCounters
+            | Tab::ShaderVariants
+            | Tab::ShaderTranslation
+            | Tab::ExternalComparison
+            | Tab::ExerciseChecks
             | Tab::Performance
+            | Tab::FramePacing
+            | Tab::CpuProfiler
+            | Tab::PuffinViewer
             | Tab::CommandRecording
             | Tab::ApiCoverage
             | Tab::ApiReference
             | Tab::Tutorials
             | Tab::LearningPath
             | Tab::Presets
+            | Tab::History
+            | Tab::KeyBindings
             | Tab::Settings => {
                 self.tools_section_open = true;
             }
         }
     }
 
-    /// Apply a theme to the egui context
-    pub fn apply_theme(ctx: &egui::Context, theme: Theme) {
-        match theme {
-            Theme::Light => {
-                ctx.set_visuals(egui::Visuals::light());
-            }
-            Theme::Dark => {
-                ctx.set_visuals(egui::Visuals::dark());
-            }
+    /// Apply the selected theme to the egui context and make its palette
+    /// the one previews are drawn with
+    pub fn apply_theme(&self, ctx: &egui::Context) {
+        theme::apply(ctx, &self.settings_panel.palette());
+    }
+
+    /// Give the previews of every workspace the selected theme's clear
+    /// color and backdrop
+    fn apply_theme_to_previews(&mut self) {
+        let palette = self.settings_panel.palette();
+        for workspace in &mut self.workspaces {
+            workspace.rendering_panel.apply_theme(&palette);
         }
     }
 
@@ -803,50 +1935,70 @@ impl PlaygroundApp {
     }
 
     /// Export the current playground state
+    ///
+    /// Panel state comes from the active workspace.
     pub fn export_state(&self) -> wgpu_playground_core::state::PlaygroundState {
         wgpu_playground_core::state::PlaygroundState {
             version: "1.0".to_string(),
             theme: self.settings_panel.get_theme(),
-            buffer_panel: Some(self.buffer_panel.export_state()),
-            texture_panel: Some(self.texture_panel.export_state()),
-            sampler_panel: Some(self.sampler_panel.export_state()),
-            shader_editor: Some(self.rendering_panel.export_shader_editor_state()),
-            render_pipeline_panel: None, // TODO: Add when RenderPipelinePanel has export_state
-            compute_pipeline_panel: None, // TODO: Add when ComputePipelinePanel has export_state
-            bind_group_panel: None,      // TODO: Add when BindGroupPanel has export_state
-            bind_group_layout_panel: None, // TODO: Add when BindGroupLayoutPanel has export_state
-            api_coverage: None,          // API coverage is tracked globally, not exported per-state
-            tutorial_state: Some(self.tutorial_panel.export_state()),
+            language: Some(i18n::language()),
+            custom_theme: Some(self.settings_panel.custom_theme().clone()),
+            api_coverage: None, // API coverage is tracked globally, not exported per-state
+            tutorial_state: Some(TutorialState {
+                lessons: self.lesson_panel.progress().clone(),
+                ..self.tutorial_panel.export_state()
+            }),
             learning_progress: Some(self.learning_path_panel.progress().clone()),
+            device_config: Some(self.device_config.get_config().to_state()),
+            ..self.workspace().export_state()
         }
     }
 
     /// Import state into the playground
+    ///
+    /// Panel state goes into the active workspace.
     pub fn import_state(&mut self, state: &wgpu_playground_core::state::PlaygroundState) {
         // Import theme preference
+        let previous_palette = self.settings_panel.palette();
         self.settings_panel.set_theme(state.theme);
-
-        if let Some(buffer_state) = &state.buffer_panel {
-            self.buffer_panel.import_state(buffer_state);
-        }
-        if let Some(texture_state) = &state.texture_panel {
-            self.texture_panel.import_state(texture_state);
+        if let Some(palette) = &state.custom_theme {
+            self.settings_panel.set_custom_theme(palette.clone());
         }
-        if let Some(sampler_state) = &state.sampler_panel {
-            self.sampler_panel.import_state(sampler_state);
+        if self.settings_panel.palette() != previous_palette {
+            self.apply_theme_to_previews();
         }
-        if let Some(shader_state) = &state.shader_editor {
-            self.rendering_panel
-                .import_shader_editor_state(shader_state);
+        if let Some(language) = &state.language {
+            if !i18n::set_language(language) {
+                log::warn!(
+                    "No translation for language {}, keeping {}",
+                    language,
+                    i18n::language()
+                );
+            }
         }
+
+        self.workspace_mut().import_state(state);
         if let Some(tutorial_state) = &state.tutorial_state {
             self.tutorial_panel.import_state(tutorial_state);
+            self.lesson_panel
+                .set_progress(tutorial_state.lessons.clone());
         }
         if let Some(learning_progress) = &state.learning_progress {
             self.learning_path_panel
                 .set_progress(learning_progress.clone());
         }
-        // TODO: Import other panel states when available
+        if let Some(device_config) = &state.device_config {
+            self.device_config
+                .set_config(DeviceConfig::from_state(device_config));
+        }
+    }
+
+    fn workspace(&self) -> &Workspace {
+        &self.workspaces[self.active_workspace]
+    }
+
+    fn workspace_mut(&mut self) -> &mut Workspace {
+        &mut self.workspaces[self.active_workspace]
     }
 
     /// Save the current state to a file
@@ -889,7 +2041,19 @@ impl PlaygroundApp {
     pub fn try_load_from_browser_url(&mut self) {
         if let Some(window) = web_sys::window() {
             if let Ok(location) = window.location().href() {
-                if location.contains("?state=") {
+                if permalink::contains_permalink(&location) {
+                    match self.import_permalink(&location) {
+                        Ok(()) => {
+                            self.share_message =
+                                Some("✓ Loaded shared configuration from URL".to_string());
+                        }
+                        Err(e) => {
+                            log::error!("Failed to load permalink from URL: {}", e);
+                            self.share_message =
+                                Some(format!("✗ Failed to load shared state: {}", e));
+                        }
+                    }
+                } else if location.contains("?state=") {
                     match self.load_state_from_url(&location) {
                         Ok(_) => {
                             log::info!("Successfully loaded shared state from URL");
@@ -913,12 +2077,374 @@ impl PlaygroundApp {
         // No-op for native builds - URL state loading only works in browser
     }
 
-    /// Handle dropped image file
-    pub fn handle_dropped_image(&mut self, bytes: Vec<u8>) {
-        self.texture_panel.load_from_bytes(bytes);
-        // Switch to texture tab to show the loaded texture
-        self.selected_tab = Tab::TextureConfig;
+    /// Returns true once if a screenshot was requested via hotkey or toolbar
+    ///
+    /// The caller captures the frame after it has been rendered and passes
+    /// the result to [`Self::finish_screenshot`].
+    pub fn take_screenshot_request(&mut self) -> bool {
+        std::mem::take(&mut self.screenshot_requested)
+    }
+
+    /// Paste the clipboard's image on the next frame if the Textures tab is
+    /// open, for Ctrl+V pressed outside text fields
+    ///
+    /// egui turns Ctrl+V into a text paste without passing the key on, so
+    /// the event loop reports it here.
+    pub fn request_clipboard_paste(&mut self) {
+        self.clipboard_paste_requested = true;
+    }
+
+    /// Save a captured frame, copy it to the clipboard and notify the user
+    pub fn finish_screenshot(
+        &mut self,
+        ctx: &egui::Context,
+        capture: wgpu_playground_core::frame_capture::CaptureResult,
+    ) {
+        use wgpu_playground_core::frame_capture;
+
+        let result = capture.and_then(|image| {
+            let dir = match self.screenshot_dir.trim() {
+                "" => frame_capture::DEFAULT_SCREENSHOT_DIR,
+                dir => dir,
+            };
+            let dir = std::path::Path::new(dir);
+            let path = frame_capture::save_frame(&image, dir)?;
+            ctx.copy_image(frame_capture::to_color_image(&image));
+            Ok((path, image))
+        });
+        let result = result.map(|(path, image)| {
+            // Make the frame available for comparison against external
+            // references and for the checks on the rendered output
+            self.lesson_panel.set_rendered_output(image.clone());
+            self.exercise_panel.set_rendered_output(image.clone());
+            self.workspace_mut()
+                .external_comparison_panel
+                .set_playground_image(image);
+            path
+        });
+
+        let (message, success) = match result {
+            Ok(path) => {
+                let message = format!(
+                    "📸 Screenshot saved to {} and copied to clipboard",
+                    path.display()
+                );
+                self.console_panel.info(message.clone());
+                (message, true)
+            }
+            Err(e) => {
+                let message = format!("✗ Screenshot failed: {}", e);
+                self.console_panel.error(message.clone());
+                (message, false)
+            }
+        };
+        self.toast = Some(Toast {
+            message,
+            success,
+            expires_at: ctx.input(|i| i.time) + TOAST_DURATION,
+        });
+    }
+
+    /// Panel and pipeline a RenderDoc capture is tagged with
+    fn capture_label(&self) -> String {
+        let workspace = &self.workspaces[self.active_workspace];
+        let detail = match self.selected_tab {
+            Tab::Rendering => workspace
+                .rendering_panel
+                .selected_example_name()
+                .map(str::to_string),
+            Tab::RenderPipelineConfig => {
+                Some(workspace.render_pipeline_panel.export_state().label)
+            }
+            Tab::ComputePipelineConfig => {
+                Some(workspace.compute_pipeline_panel.export_state().label)
+            }
+            _ => None,
+        };
+        capture_label(&format!("{:?}", self.selected_tab), detail.as_deref())
+    }
+
+    /// Start a requested RenderDoc capture; called before the frame's work
+    /// is recorded
+    pub fn begin_renderdoc_capture(&mut self) {
+        self.renderdoc.begin_frame();
+    }
+
+    /// End a RenderDoc capture begun this frame and notify the user
+    pub fn finish_renderdoc_capture(&mut self, ctx: &egui::Context) {
+        let Some(result) = self.renderdoc.end_frame() else {
+            return;
+        };
+        let (message, success) = match result {
+            Ok(capture) => {
+                let message = format!(
+                    "🎯 RenderDoc capture \"{}\" saved to {}",
+                    capture.label,
+                    capture.path.display()
+                );
+                self.console_panel.info(message.clone());
+                (message, true)
+            }
+            Err(e) => {
+                let message = format!("✗ RenderDoc capture failed: {}", e);
+                self.console_panel.error(message.clone());
+                (message, false)
+            }
+        };
+        self.toast = Some(Toast {
+            message,
+            success,
+            expires_at: ctx.input(|i| i.time) + TOAST_DURATION,
+        });
+    }
+
+    /// Returns true once if a simulated device loss was requested
+    pub fn take_device_loss_request(&mut self) -> bool {
+        std::mem::take(&mut self.device_loss_requested)
+    }
+
+    /// Record the configuration the current device was created with
+    pub fn set_device_config(&mut self, config: DeviceConfig) {
+        self.device_config.set_active(config);
+    }
+
+    /// Returns the configuration to recreate the device with, once per apply
+    pub fn take_device_config_request(&mut self) -> Option<DeviceConfig> {
+        self.device_config.take_apply_request()
+    }
+
+    /// Tell the user whether the device was recreated with a new configuration
+    ///
+    /// `result` holds the new adapter name, or why no device could be
+    /// requested.
+    pub fn report_device_config(&mut self, ctx: &egui::Context, result: Result<String, String>) {
+        let (message, success) = match &result {
+            Ok(adapter_name) => {
+                let message = format!("♻ Device recreated on {}", adapter_name);
+                self.console_panel.info(message.clone());
+                (message, true)
+            }
+            Err(e) => {
+                let message = format!("✗ Device not recreated: {}", e);
+                self.console_panel.error(message.clone());
+                (message, false)
+            }
+        };
+        self.device_config.finish_apply(result);
+        self.toast = Some(Toast {
+            message,
+            success,
+            expires_at: ctx.input(|i| i.time) + TOAST_DURATION,
+        });
+    }
+
+    /// Record how the window surface is configured and what it supports
+    pub fn set_surface_config(
+        &mut self,
+        config: &wgpu::SurfaceConfiguration,
+        capabilities: &wgpu::SurfaceCapabilities,
+        view_formats_supported: bool,
+    ) {
+        self.surface_config_panel
+            .set_active(config, capabilities, view_formats_supported);
+        self.frame_pacing_panel.set_active(
+            PresentSettings::from_config(config),
+            &capabilities.present_modes,
+        );
+    }
+
+    /// Returns the surface settings to reconfigure the surface with, once
+    /// per change
+    pub fn take_surface_settings_request(&mut self) -> Option<SurfaceSettings> {
+        self.surface_config_panel.take_apply_request()
+    }
+
+    /// Show which requested surface settings could not be applied
+    pub fn report_surface_settings(&mut self, rejected: Vec<String>) {
+        for message in &rejected {
+            self.console_panel.warning(message.clone());
+        }
+        self.surface_config_panel.report_rejected(rejected);
+    }
+
+    /// Where to draw the gamma test card of the Surface Config tab this
+    /// frame, if it is shown
+    pub fn take_overlay_target(&mut self) -> Option<OverlayTarget> {
+        self.surface_config_panel.take_overlay_target()
+    }
+
+    /// Returns the present settings to reconfigure the surface with, once
+    /// per change
+    pub fn take_present_settings_request(&mut self) -> Option<PresentSettings> {
+        self.frame_pacing_panel.take_apply_request()
+    }
+
+    /// Timer the render loop reports each frame's stages to
+    pub fn frame_timer(&mut self) -> &mut FrameTimer {
+        self.frame_pacing_panel.timer_mut()
+    }
+
+    /// Rebuild GPU resources of all workspaces on a replacement device
+    pub fn recreate_resources(
+        &mut self,
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        self.device_info = DeviceInfo::new(adapter, device);
+        self.device_config.set_adapter(adapter);
+        self.frame_pacing_panel.recreate_resources(device, queue);
+        for workspace in &mut self.workspaces {
+            workspace.recreate_resources(device, queue);
+        }
+    }
+
+    /// Tell the user about a device loss and whether recovery worked
+    ///
+    /// `recovery` holds the new adapter name, or why no device could be
+    /// requested.
+    pub fn report_device_loss(
+        &mut self,
+        ctx: &egui::Context,
+        loss: &DeviceLoss,
+        recovery: Result<String, String>,
+    ) {
+        let cause = if loss.simulated {
+            "Simulated device loss".to_string()
+        } else {
+            format!("GPU device lost ({}): {}", loss.reason, loss.message)
+        };
+        let (message, success) = match recovery {
+            Ok(adapter_name) => {
+                let message = format!("♻ {}; recovered on {}", cause, adapter_name);
+                self.console_panel.warning(message.clone());
+                (message, true)
+            }
+            Err(e) => {
+                let message = format!("✗ {}; no new device yet: {}", cause, e);
+                self.console_panel.error(message.clone());
+                (message, false)
+            }
+        };
+        self.toast = Some(Toast {
+            message,
+            success,
+            expires_at: ctx.input(|i| i.time) + TOAST_DURATION,
+        });
+    }
+
+    /// Open a file dropped on the window in the panel for its type,
+    /// reporting the outcome in a toast
+    fn open_dropped_file(&mut self, file: &egui::DroppedFile, now: f64) {
+        let name = drop_router::dropped_file_name(file);
+        let (message, success) = match DropTarget::for_dropped(file) {
+            Some(target) => match self.route_dropped_file(target, file) {
+                Ok(()) => (
+                    tr_args("drop-opened", &[("file", &name), ("target", &target.name())]),
+                    true,
+                ),
+                Err(error) => (
+                    tr_args("drop-failed", &[("file", &name), ("error", &error)]),
+                    false,
+                ),
+            },
+            None => (tr_args("drop-unsupported", &[("file", &name)]), false),
+        };
+        if success {
+            self.console_panel.info(message.clone());
+        } else {
+            self.console_panel.warning(message.clone());
+        }
+        self.toast = Some(Toast {
+            message,
+            success,
+            expires_at: now + TOAST_DURATION,
+        });
     }
+
+    /// Load the clipboard's image in the Textures tab and notify the user
+    #[cfg(not(target_arch = "wasm32"))]
+    fn paste_clipboard_image(&mut self, ctx: &egui::Context, now: f64) {
+        // Screenshots are taken at the display's resolution
+        let dpi = ctx.native_pixels_per_point().map(|scale| scale * BASE_DPI);
+        let (message, success) = match self.workspace_mut().texture_panel.paste_from_clipboard(dpi)
+        {
+            Ok((width, height)) => (
+                tr_args(
+                    "paste-image-done",
+                    &[("width", &width), ("height", &height)],
+                ),
+                true,
+            ),
+            Err(error) => (tr_args("paste-image-failed", &[("error", &error)]), false),
+        };
+        if success {
+            self.console_panel.info(message.clone());
+        } else {
+            self.console_panel.warning(message.clone());
+        }
+        self.toast = Some(Toast {
+            message,
+            success,
+            expires_at: now + TOAST_DURATION,
+        });
+    }
+
+    /// Hand a dropped file to the panel of `target` and switch to that panel
+    ///
+    /// Images and models finish loading in the background; their panels
+    /// show any decode errors.
+    fn route_dropped_file(
+        &mut self,
+        target: DropTarget,
+        file: &egui::DroppedFile,
+    ) -> Result<(), String> {
+        let tab = match target {
+            DropTarget::ShaderEditor => {
+                let bytes = drop_router::read_dropped_file(file).map_err(|e| e.to_string())?;
+                let source = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+                let name = drop_router::dropped_file_name(file);
+                self.workspace_mut()
+                    .rendering_panel
+                    .open_shader(&name, source);
+                Tab::Rendering
+            }
+            DropTarget::Texture => {
+                let bytes = drop_router::read_dropped_file(file).map_err(|e| e.to_string())?;
+                self.workspace_mut().texture_panel.load_from_bytes(bytes);
+                Tab::TextureConfig
+            }
+            DropTarget::Model => {
+                // The importer reads materials and buffers next to the file
+                let path = file.path.clone().ok_or_else(|| tr("drop-needs-path"))?;
+                self.workspace_mut().model_loader_panel.load_path(path);
+                Tab::ModelLoader
+            }
+            DropTarget::Project => {
+                let bytes = drop_router::read_dropped_file(file).map_err(|e| e.to_string())?;
+                let json = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+                let state = wgpu_playground_core::state::PlaygroundState::from_json(&json)
+                    .map_err(|e| e.to_string())?;
+                self.import_state(&state);
+                return Ok(());
+            }
+        };
+        self.selected_tab = tab;
+        self.open_section_for_tab(tab);
+        Ok(())
+    }
+}
+
+/// Whether this frame's input can have finished an edit: a released
+/// pointer button, a key press, typed or pasted text, or dropped files
+fn ends_interaction(input: &egui::InputState) -> bool {
+    !input.raw.dropped_files.is_empty()
+        || input.events.iter().any(|event| match event {
+            egui::Event::PointerButton { pressed, .. } => !pressed,
+            egui::Event::Key { pressed, .. } => *pressed,
+            egui::Event::Text(_) | egui::Event::Paste(_) | egui::Event::Cut => true,
+            _ => false,
+        })
 }
 
 #[cfg(test)]
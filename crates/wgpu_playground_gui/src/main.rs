@@ -1,16 +1,41 @@
 use egui_wgpu::ScreenDescriptor;
 use pollster::FutureExt;
+use std::process::ExitCode;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use winit::{
     application::ApplicationHandler,
     event::WindowEvent,
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    keyboard::Key,
     window::{Window, WindowId},
 };
 
 mod app;
+mod watch;
+mod workspace;
 
 use app::PlaygroundApp;
+use wgpu_playground_core::bindless;
+use wgpu_playground_core::cpu_profiler::{
+    CpuProfiler, SCOPE_ACQUIRE, SCOPE_PRESENT, SCOPE_RECORD, SCOPE_SUBMIT,
+};
+use wgpu_playground_core::cpu_scope;
+use wgpu_playground_core::device_config::DeviceConfig;
+use wgpu_playground_core::device_pipeline_cache::{
+    self, DevicePipelineCache, DevicePipelineCacheError,
+};
+use wgpu_playground_core::device_recovery::{DeviceLoss, DeviceLossMonitor};
+use wgpu_playground_core::frame_pacing::PresentSettings;
+use wgpu_playground_core::hardware_ray_tracing;
+use wgpu_playground_core::mesh_shading;
+use wgpu_playground_core::surface_config::{GammaOverlay, SurfaceSettings};
+use wgpu_playground_core::surface_config_panel::OverlayTarget;
+use wgpu_playground_core::upload_belt::{self, UploadBelt};
+use wgpu_playground_core::workgroup_tuner;
+
+/// Wait between attempts to get a device after a failed recovery
+const RECOVERY_RETRY_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Debug)]
 enum RenderError {
@@ -20,10 +45,23 @@ enum RenderError {
 
 struct AppState {
     window: Arc<Window>,
+    instance: wgpu::Instance,
+    adapter_options: wgpu_playground_core::adapter::AdapterOptions,
+    adapter: wgpu::Adapter,
     surface: wgpu::Surface<'static>,
     device: wgpu::Device,
     queue: wgpu::Queue,
+    /// Features and limits requested for the current and replacement devices
+    device_config: DeviceConfig,
+    device_loss: DeviceLossMonitor,
+    /// Loss waiting for a replacement device, with the time of the next attempt
+    pending_loss: Option<(DeviceLoss, Instant)>,
     surface_config: wgpu::SurfaceConfiguration,
+    /// What the surface supports on the current adapter
+    surface_caps: wgpu::SurfaceCapabilities,
+    /// Gamma test card of the Surface Config tab, for the format it was
+    /// last drawn with
+    gamma_overlay: Option<GammaOverlay>,
     egui_renderer: egui_wgpu::Renderer,
     egui_state: egui_winit::State,
     egui_ctx: egui::Context,
@@ -59,7 +97,13 @@ impl AppState {
                 }
             });
 
-        let instance = wgpu_playground_core::adapter::create_instance(backends);
+        // Fail allocations near the native budget instead of paging
+        let instance = wgpu_playground_core::adapter::create_instance_from(
+            &wgpu_playground_core::adapter::InstanceOptions::new(backends)
+                .with_memory_budget_thresholds(
+                    wgpu_playground_core::memory_budget::BUDGET_THRESHOLDS,
+                ),
+        );
 
         let surface = instance
             .create_surface(window.clone())
@@ -82,42 +126,39 @@ impl AppState {
             wgpu_playground_core::adapter::backend_to_str(&adapter.get_info().backend)
         );
 
+        // The saved state also holds the device configuration, so read it
+        // before requesting the device
+        let state_path = std::path::Path::new("playground_state.json");
+        let saved_state = if state_path.exists() {
+            wgpu_playground_core::state::PlaygroundState::load_from_file(state_path)
+                .inspect_err(|e| log::warn!("Failed to load saved state: {}", e))
+                .ok()
+        } else {
+            None
+        };
+        let device_config = saved_state
+            .as_ref()
+            .and_then(|state| state.device_config.as_ref())
+            .map(DeviceConfig::from_state)
+            .unwrap_or_default()
+            .restricted_to(adapter.features(), &adapter.limits());
+
         let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor {
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::default(),
-                label: Some("WebGPU Playground Device"),
-                memory_hints: Default::default(),
-                experimental_features: Default::default(),
-                trace: wgpu::Trace::Off,
-            })
+            .request_device(&device_descriptor(&device_config, &adapter))
             .await
             .expect("Failed to create device");
+        open_pipeline_cache(&adapter, &device);
+        UploadBelt::global().open(&device, &queue);
 
-        // Set up comprehensive error handling for the device
-        // This configures callbacks for device loss and uncaptured errors
-        wgpu_playground_core::error::setup_device_error_handling(&device);
-
-        let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(surface_caps.formats[0]);
-
-        let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: surface_format,
-            width: size.width,
-            height: size.height,
-            present_mode: surface_caps.present_modes[0],
-            alpha_mode: surface_caps.alpha_modes[0],
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
-        };
+        // Panels capture their own errors; only log what escapes them.
+        // Device loss is handled by the monitor
+        wgpu_playground_core::error::log_uncaptured_errors(&device);
+        let device_loss = DeviceLossMonitor::new();
+        device_loss.watch(&device);
 
+        let surface_config = surface_config(&surface, &adapter, size);
         surface.configure(&device, &surface_config);
+        let surface_caps = surface.get_capabilities(&adapter);
 
         let egui_ctx = egui::Context::default();
         let egui_state = egui_winit::State::new(
@@ -129,51 +170,40 @@ impl AppState {
             None,
         );
 
-        let egui_renderer = egui_wgpu::Renderer::new(
-            &device,
-            surface_config.format,
-            egui_wgpu::RendererOptions {
-                msaa_samples: 1,
-                ..Default::default()
-            },
-        );
+        let egui_renderer = egui_renderer(&device, surface_config.format);
 
         let mut playground_app = PlaygroundApp::new(&adapter, &device, &queue);
+        playground_app.set_device_config(device_config.clone());
+        playground_app.set_surface_config(
+            &surface_config,
+            &surface_caps,
+            supports_view_formats(&adapter),
+        );
 
         // Try to load state from URL if present (mainly for WASM/web builds)
         playground_app.try_load_from_browser_url();
 
-        // Try to load saved state and apply theme
-        let state_path = std::path::Path::new("playground_state.json");
-        let (playground_app, initial_theme) = if state_path.exists() {
-            match wgpu_playground_core::state::PlaygroundState::load_from_file(state_path) {
-                Ok(state) => {
-                    let theme = state.theme;
-                    let mut app = playground_app;
-                    app.import_state(&state);
-                    log::info!("Loaded saved state with theme: {:?}", theme);
-                    (app, Some(theme))
-                }
-                Err(e) => {
-                    log::warn!("Failed to load saved state: {}", e);
-                    (playground_app, None)
-                }
-            }
-        } else {
-            (playground_app, None)
-        };
-
-        // Apply the theme if we loaded one
-        if let Some(theme) = initial_theme {
-            PlaygroundApp::apply_theme(&egui_ctx, theme);
+        // Apply the saved state and theme
+        if let Some(state) = saved_state {
+            playground_app.import_state(&state);
+            log::info!("Loaded saved state with theme: {:?}", state.theme);
         }
+        playground_app.apply_theme(&egui_ctx);
 
         Self {
             window,
+            instance,
+            adapter_options,
+            adapter,
             surface,
             device,
             queue,
+            device_config,
+            device_loss,
+            pending_loss: None,
             surface_config,
+            surface_caps,
+            gamma_overlay: None,
             egui_renderer,
             egui_state,
             egui_ctx,
@@ -189,17 +219,314 @@ impl AppState {
         }
     }
 
+    /// Request a new device with the current configuration and rebuild
+    /// everything created on the old one
+    ///
+    /// Returns the new adapter name, or why no device could be requested.
+    /// The old device is kept if no new one could be requested.
+    async fn replace_device(&mut self) -> Result<String, String> {
+        let adapter = wgpu_playground_core::adapter::request_adapter(
+            &self.instance,
+            &self.adapter_options,
+            Some(&self.surface),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        // A replacement adapter may support less than the previous one
+        let device_config = self
+            .device_config
+            .restricted_to(adapter.features(), &adapter.limits());
+        let (device, queue) = adapter
+            .request_device(&device_descriptor(&device_config, &adapter))
+            .await
+            .map_err(|e| format!("Failed to create device: {}", e))?;
+        self.device_config = device_config;
+        open_pipeline_cache(&adapter, &device);
+        UploadBelt::global().open(&device, &queue);
+
+        wgpu_playground_core::error::log_uncaptured_errors(&device);
+        self.device_loss.watch(&device);
+
+        // Keep the current size; the window may be minimized right now
+        // and the settings chosen in the Surface Config and Frame Pacing tabs
+        // as far as the new adapter supports them
+        let size =
+            winit::dpi::PhysicalSize::new(self.surface_config.width, self.surface_config.height);
+        let settings = SurfaceSettings::from_config(&self.surface_config);
+        let present = PresentSettings::from_config(&self.surface_config);
+        self.surface_config = surface_config(&self.surface, &adapter, size);
+        self.surface_caps = self.surface.get_capabilities(&adapter);
+        settings.apply_to(&mut self.surface_config, &self.surface_caps);
+        present.apply_to(&mut self.surface_config, &self.surface_caps.present_modes);
+        if !supports_view_formats(&adapter) {
+            self.surface_config.view_formats.clear();
+        }
+        self.surface.configure(&device, &self.surface_config);
+        self.gamma_overlay = None;
+
+        self.rebuild_egui(&device);
+
+        self.playground_app
+            .recreate_resources(&adapter, &device, &queue);
+        self.device = device;
+        self.queue = queue;
+        let name = adapter.get_info().name;
+        self.adapter = adapter;
+        self.sync_surface_config();
+
+        Ok(name)
+    }
+
+    /// Start over with a new egui context and renderer for the current
+    /// surface format
+    ///
+    /// egui only uploads its textures once, so the new context keeps just
+    /// the UI memory (open sections, scroll positions) and uploads them
+    /// again.
+    fn rebuild_egui(&mut self, device: &wgpu::Device) {
+        let memory = self.egui_ctx.memory(|memory| memory.clone());
+        self.egui_ctx = egui::Context::default();
+        self.egui_ctx.memory_mut(|new_memory| *new_memory = memory);
+        self.playground_app.apply_theme(&self.egui_ctx);
+        self.egui_state = egui_winit::State::new(
+            self.egui_ctx.clone(),
+            egui::ViewportId::ROOT,
+            &self.window,
+            None,
+            None,
+            None,
+        );
+        self.egui_renderer = egui_renderer(device, self.surface_config.format);
+    }
+
+    /// Show the current surface configuration in the UI
+    fn sync_surface_config(&mut self) {
+        self.playground_app.set_surface_config(
+            &self.surface_config,
+            &self.surface_caps,
+            supports_view_formats(&self.adapter),
+        );
+    }
+
+    /// Reconfigure the surface if another format, present mode, alpha mode
+    /// or view format was chosen in the UI
+    fn handle_surface_settings(&mut self) {
+        let Some(settings) = self.playground_app.take_surface_settings_request() else {
+            return;
+        };
+        let previous_format = self.surface_config.format;
+        let mut rejected = settings.apply_to(&mut self.surface_config, &self.surface_caps);
+        if !self.surface_config.view_formats.is_empty() && !supports_view_formats(&self.adapter) {
+            self.surface_config.view_formats.clear();
+            rejected.push("The adapter does not support surface view formats".to_string());
+        }
+        for message in &rejected {
+            log::warn!("{}", message);
+        }
+        self.surface.configure(&self.device, &self.surface_config);
+        log::info!(
+            "Surface reconfigured: {:?}, {:?}, {:?}, view formats {:?}",
+            self.surface_config.format,
+            self.surface_config.present_mode,
+            self.surface_config.alpha_mode,
+            self.surface_config.view_formats
+        );
+
+        if self.surface_config.format != previous_format {
+            // egui's pipeline targets one format, and textures registered
+            // with the old renderer are gone with it
+            let device = self.device.clone();
+            self.rebuild_egui(&device);
+            self.playground_app
+                .recreate_resources(&self.adapter, &self.device, &self.queue);
+        }
+        self.sync_surface_config();
+        self.playground_app.report_surface_settings(rejected);
+        self.playground_app.frame_timer().clear();
+    }
+
+    /// Draw the gamma test card of the Surface Config tab over the UI
+    fn render_gamma_overlay(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+        target: OverlayTarget,
+    ) {
+        if self.surface_config.format != target.format
+            && !self.surface_config.view_formats.contains(&target.format)
+        {
+            return;
+        }
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(target.format),
+            ..Default::default()
+        });
+        let overlay = match self.gamma_overlay.take() {
+            Some(overlay) if overlay.format() == target.format => overlay,
+            _ => GammaOverlay::new(&self.device, target.format),
+        };
+
+        let scale = self.window.scale_factor() as f32;
+        let pixels = |rect: egui::Rect| {
+            [
+                rect.left() * scale,
+                rect.top() * scale,
+                rect.width() * scale,
+                rect.height() * scale,
+            ]
+        };
+        overlay.render(
+            encoder,
+            &view,
+            [self.surface_config.width, self.surface_config.height],
+            pixels(target.rect),
+            pixels(target.clip),
+        );
+        self.gamma_overlay = Some(overlay);
+    }
+
+    /// Recreate the device if a new configuration was applied in the UI
+    fn handle_device_config(&mut self) {
+        let Some(config) = self.playground_app.take_device_config_request() else {
+            return;
+        };
+        let previous = std::mem::replace(&mut self.device_config, config);
+        let result = self.replace_device().block_on();
+        match &result {
+            Ok(name) => log::info!("Recreated device on {} with the new configuration", name),
+            Err(e) => {
+                log::error!("Device recreation failed: {}", e);
+                self.device_config = previous;
+            }
+        }
+        self.playground_app
+            .report_device_config(&self.egui_ctx, result);
+    }
+
+    /// Reconfigure the surface if another present mode or frame latency
+    /// was chosen in the UI
+    fn handle_present_settings(&mut self) {
+        let Some(settings) = self.playground_app.take_present_settings_request() else {
+            return;
+        };
+        if !settings.apply_to(&mut self.surface_config, &self.surface_caps.present_modes) {
+            log::warn!(
+                "Present mode {:?} is not supported by the surface, using Fifo",
+                settings.present_mode
+            );
+        }
+        self.surface.configure(&self.device, &self.surface_config);
+        log::info!(
+            "Surface reconfigured with present mode {:?} and frame latency {}",
+            self.surface_config.present_mode,
+            self.surface_config.desired_maximum_frame_latency
+        );
+        self.sync_surface_config();
+        // Timings from the previous mode would blur the comparison
+        self.playground_app.frame_timer().clear();
+    }
+
+    /// Start or retry recovery from a device loss
+    ///
+    /// Returns true while the device is unusable and the frame must be
+    /// skipped.
+    fn handle_device_loss(&mut self) -> bool {
+        if self.playground_app.take_device_loss_request() {
+            self.device_loss.simulate(&self.device);
+        }
+        if let Some(loss) = self.device_loss.take() {
+            self.pending_loss = Some((loss, Instant::now()));
+        }
+        let Some((loss, retry_at)) = self.pending_loss.take() else {
+            return false;
+        };
+        if Instant::now() < retry_at {
+            self.pending_loss = Some((loss, retry_at));
+            return true;
+        }
+
+        let result = self.replace_device().block_on();
+        let recovered = result.is_ok();
+        match &result {
+            Ok(name) => log::info!("Recovered from device loss on {}", name),
+            Err(e) => log::error!("Device recovery failed: {}", e),
+        }
+        self.playground_app
+            .report_device_loss(&self.egui_ctx, &loss, result);
+        if !recovered {
+            self.pending_loss = Some((loss, Instant::now() + RECOVERY_RETRY_INTERVAL));
+        }
+        !recovered
+    }
+
     fn render(&mut self) -> Result<(), RenderError> {
-        let surface_texture = match self.surface.get_current_texture() {
-            wgpu::CurrentSurfaceTexture::Success(t)
-            | wgpu::CurrentSurfaceTexture::Suboptimal(t) => t,
-            wgpu::CurrentSurfaceTexture::Outdated | wgpu::CurrentSurfaceTexture::Lost => {
-                return Err(RenderError::SurfaceReconfigure)
+        if self.handle_device_loss() {
+            return Ok(());
+        }
+        self.handle_device_config();
+        self.handle_present_settings();
+        self.handle_surface_settings();
+
+        self.playground_app.frame_timer().begin_frame();
+        let profiler = CpuProfiler::global();
+        profiler.begin_frame();
+        let surface_texture = {
+            cpu_scope!(SCOPE_ACQUIRE);
+            match self.surface.get_current_texture() {
+                wgpu::CurrentSurfaceTexture::Success(t)
+                | wgpu::CurrentSurfaceTexture::Suboptimal(t) => t,
+                wgpu::CurrentSurfaceTexture::Outdated | wgpu::CurrentSurfaceTexture::Lost => {
+                    self.playground_app.frame_timer().cancel_frame();
+                    profiler.cancel_frame();
+                    return Err(RenderError::SurfaceReconfigure);
+                }
+                wgpu::CurrentSurfaceTexture::Timeout
+                | wgpu::CurrentSurfaceTexture::Occluded
+                | wgpu::CurrentSurfaceTexture::Validation => {
+                    self.playground_app.frame_timer().cancel_frame();
+                    profiler.cancel_frame();
+                    return Ok(());
+                }
             }
-            wgpu::CurrentSurfaceTexture::Timeout
-            | wgpu::CurrentSurfaceTexture::Occluded
-            | wgpu::CurrentSurfaceTexture::Validation => return Ok(()),
         };
+        self.playground_app.frame_timer().acquired();
+        self.playground_app.begin_renderdoc_capture();
+        let command_buffer = self.record_frame(&surface_texture);
+
+        {
+            cpu_scope!(SCOPE_SUBMIT);
+            // Runs any uploads the previews staged without submitting
+            upload_belt::submit(&self.queue, std::iter::once(command_buffer));
+        }
+        UploadBelt::global().end_frame();
+        self.playground_app.frame_timer().submitted(&self.queue);
+
+        // Capture the finished frame before presenting it
+        if self.playground_app.take_screenshot_request() {
+            cpu_scope!("Capture");
+            let capture = wgpu_playground_core::frame_capture::capture_frame(
+                &self.device,
+                &self.queue,
+                &surface_texture.texture,
+            );
+            self.playground_app
+                .finish_screenshot(&self.egui_ctx, capture);
+        }
+
+        {
+            cpu_scope!(SCOPE_PRESENT);
+            surface_texture.present();
+        }
+        self.playground_app.frame_timer().presented();
+        self.playground_app.finish_renderdoc_capture(&self.egui_ctx);
+        profiler.end_frame();
+
+        Ok(())
+    }
+
+    /// Record the frame's commands: the clear, the UI and any overlay
+    fn record_frame(&mut self, surface_texture: &wgpu::SurfaceTexture) -> wgpu::CommandBuffer {
+        cpu_scope!(SCOPE_RECORD);
         let view = surface_texture
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
@@ -212,6 +539,7 @@ impl AppState {
 
         // Clear the screen
         {
+            cpu_scope!("Clear pass");
             let _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Clear Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -236,39 +564,48 @@ impl AppState {
         }
 
         // Run egui
-        let raw_input = self.egui_state.take_egui_input(&self.window);
-        let egui_output = self.egui_ctx.run_ui(raw_input, |ui| {
-            self.playground_app
-                .ui(ui, &self.device, &self.queue, &mut self.egui_renderer);
-        });
+        let egui_output = {
+            cpu_scope!("UI");
+            let raw_input = self.egui_state.take_egui_input(&self.window);
+            self.egui_ctx.run_ui(raw_input, |ui| {
+                self.playground_app
+                    .ui(ui, &self.device, &self.queue, &mut self.egui_renderer);
+            })
+        };
 
         self.egui_state
             .handle_platform_output(&self.window, egui_output.platform_output);
 
-        let clipped_primitives = self
-            .egui_ctx
-            .tessellate(egui_output.shapes, egui_output.pixels_per_point);
+        let clipped_primitives = {
+            cpu_scope!("Tessellate");
+            self.egui_ctx
+                .tessellate(egui_output.shapes, egui_output.pixels_per_point)
+        };
 
         let screen_descriptor = ScreenDescriptor {
             size_in_pixels: [self.surface_config.width, self.surface_config.height],
             pixels_per_point: self.window.scale_factor() as f32,
         };
 
-        for (id, image_delta) in &egui_output.textures_delta.set {
-            self.egui_renderer
-                .update_texture(&self.device, &self.queue, *id, image_delta);
-        }
+        {
+            cpu_scope!("Upload");
+            for (id, image_delta) in &egui_output.textures_delta.set {
+                self.egui_renderer
+                    .update_texture(&self.device, &self.queue, *id, image_delta);
+            }
 
-        self.egui_renderer.update_buffers(
-            &self.device,
-            &self.queue,
-            &mut encoder,
-            &clipped_primitives,
-            &screen_descriptor,
-        );
+            self.egui_renderer.update_buffers(
+                &self.device,
+                &self.queue,
+                &mut encoder,
+                &clipped_primitives,
+                &screen_descriptor,
+            );
+        }
 
         // Render egui
         {
+            cpu_scope!("UI pass");
             let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("UI Render Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -291,17 +628,95 @@ impl AppState {
                 .render(&mut render_pass, &clipped_primitives, &screen_descriptor);
         }
 
+        if let Some(target) = self.playground_app.take_overlay_target() {
+            cpu_scope!("Gamma overlay");
+            self.render_gamma_overlay(&mut encoder, &surface_texture.texture, target);
+        }
+
         for id in &egui_output.textures_delta.free {
             self.egui_renderer.free_texture(id);
         }
 
-        self.queue.submit(std::iter::once(encoder.finish()));
-        surface_texture.present();
+        cpu_scope!("Finish");
+        encoder.finish()
+    }
+}
+
+fn surface_config(
+    surface: &wgpu::Surface<'_>,
+    adapter: &wgpu::Adapter,
+    size: winit::dpi::PhysicalSize<u32>,
+) -> wgpu::SurfaceConfiguration {
+    let surface_caps = surface.get_capabilities(adapter);
+    let surface_format = surface_caps
+        .formats
+        .iter()
+        .copied()
+        .find(|f| f.is_srgb())
+        .unwrap_or(surface_caps.formats[0]);
+
+    // COPY_SRC lets the screenshot feature read back presented frames
+    let mut surface_usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+    if surface_caps.usages.contains(wgpu::TextureUsages::COPY_SRC) {
+        surface_usage |= wgpu::TextureUsages::COPY_SRC;
+    }
 
-        Ok(())
+    wgpu::SurfaceConfiguration {
+        usage: surface_usage,
+        format: surface_format,
+        width: size.width,
+        height: size.height,
+        present_mode: surface_caps.present_modes[0],
+        alpha_mode: surface_caps.alpha_modes[0],
+        view_formats: vec![],
+        desired_maximum_frame_latency: 2,
     }
 }
 
+/// Whether surface textures may be viewed in their sRGB or linear
+/// counterpart format
+fn supports_view_formats(adapter: &wgpu::Adapter) -> bool {
+    adapter
+        .get_downlevel_capabilities()
+        .flags
+        .contains(wgpu::DownlevelFlags::SURFACE_VIEW_FORMATS)
+}
+
+/// Descriptor for a device with `config` that can also cache pipelines, time
+/// compute passes, index texture arrays, trace rays in hardware and run mesh
+/// shaders where the adapter can
+fn device_descriptor(
+    config: &DeviceConfig,
+    adapter: &wgpu::Adapter,
+) -> wgpu::DeviceDescriptor<'static> {
+    let mut descriptor = config.descriptor();
+    descriptor.required_features |= device_pipeline_cache::required_features(adapter);
+    descriptor.required_features |= workgroup_tuner::required_features(adapter);
+    bindless::request_binding_arrays(adapter, &mut descriptor);
+    hardware_ray_tracing::request_ray_queries(adapter, &mut descriptor);
+    mesh_shading::request_mesh_shaders(adapter, &mut descriptor);
+    descriptor
+}
+
+/// Load the pipelines a previous launch compiled on this adapter, saving
+/// those of the previous device first
+fn open_pipeline_cache(adapter: &wgpu::Adapter, device: &wgpu::Device) {
+    if let Err(e) = DevicePipelineCache::global().open(adapter, device) {
+        log::info!("{}", e);
+    }
+}
+
+fn egui_renderer(device: &wgpu::Device, format: wgpu::TextureFormat) -> egui_wgpu::Renderer {
+    egui_wgpu::Renderer::new(
+        device,
+        format,
+        egui_wgpu::RendererOptions {
+            msaa_samples: 1,
+            ..Default::default()
+        },
+    )
+}
+
 struct App {
     state: Option<AppState>,
 }
@@ -345,24 +760,14 @@ impl ApplicationHandler for App {
                 Ok(_) => {}
                 Err(RenderError::SurfaceReconfigure) => state.resize(state.window.inner_size()),
             },
-            WindowEvent::DroppedFile(path) => {
-                // Handle file drop
-                if let Ok(bytes) = std::fs::read(&path) {
-                    // Check if it's an image file by extension
-                    if let Some(ext) = path.extension() {
-                        match ext.to_str() {
-                            Some(ext_str) => {
-                                let ext_lower = ext_str.to_lowercase();
-                                if ext_lower == "png" || ext_lower == "jpg" || ext_lower == "jpeg" {
-                                    state.playground_app.handle_dropped_image(bytes);
-                                }
-                            }
-                            None => {
-                                log::warn!("Dropped file has invalid UTF-8 extension: {:?}", path);
-                            }
-                        }
-                    }
-                }
+            // Text fields consume Ctrl+V above; elsewhere it may paste an image
+            WindowEvent::KeyboardInput { event, .. }
+                if event.state.is_pressed()
+                    && !event.repeat
+                    && state.egui_state.egui_input().modifiers.command
+                    && matches!(&event.logical_key, Key::Character(c) if c.eq_ignore_ascii_case("v")) =>
+            {
+                state.playground_app.request_clipboard_paste();
             }
             _ => {}
         }
@@ -375,9 +780,33 @@ impl ApplicationHandler for App {
     }
 }
 
-fn main() {
+fn main() -> ExitCode {
     env_logger::init();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("watch") {
+        return watch::run(&args[1..]);
+    }
+
+    #[cfg(feature = "tracy")]
+    wgpu_playground_core::cpu_profiler::start_tracy();
+
+    // Kept alive for the whole run; dropping it stops the server
+    #[cfg(feature = "puffin")]
+    let _puffin_server = {
+        use wgpu_playground_core::cpu_profiler::{serve_puffin, PUFFIN_ADDRESS};
+        match serve_puffin(PUFFIN_ADDRESS) {
+            Ok(server) => {
+                log::info!("Serving puffin scopes on {}", PUFFIN_ADDRESS);
+                Some(server)
+            }
+            Err(e) => {
+                log::warn!("Failed to start the puffin server: {}", e);
+                None
+            }
+        }
+    };
+
     let event_loop = EventLoop::new().expect("Failed to create event loop");
     event_loop.set_control_flow(ControlFlow::Poll);
 
@@ -385,4 +814,10 @@ fn main() {
     event_loop
         .run_app(&mut app)
         .expect("Failed to run event loop");
+    // Pipelines compiled this run are loaded on the next launch
+    match DevicePipelineCache::global().save() {
+        Ok(_) | Err(DevicePipelineCacheError::NotOpen) => {}
+        Err(e) => log::warn!("Failed to save the pipeline cache: {}", e),
+    }
+    ExitCode::SUCCESS
 }
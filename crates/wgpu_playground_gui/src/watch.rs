@@ -0,0 +1,545 @@
+//! `wgpu_playground watch <project>` - headless edit-test loop
+//!
+//! Renders a saved project without opening a window, compares the frame
+//! against its visual regression reference and prints the result. The
+//! project file, the shader file it references and the assets directory
+//! are watched, and every change triggers a new render, so shader authors
+//! can keep working in their own editor.
+
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use image::RgbaImage;
+use wgpu_playground_core::adapter;
+use wgpu_playground_core::asset_watcher::AssetWatcher;
+use wgpu_playground_core::assets;
+use wgpu_playground_core::file_watcher::FileWatcher;
+use wgpu_playground_core::headless_session::Session;
+use wgpu_playground_core::state::PlaygroundState;
+use wgpu_playground_core::visual_regression::references::ReferenceStore;
+use wgpu_playground_core::visual_regression::{self, ComparisonConfig};
+
+/// How often the watchers are polled
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Editors often write a file in several steps; wait for them to settle
+const SETTLE_TIME: Duration = Duration::from_millis(150);
+
+/// Width of the diff heatmap, in terminal columns
+const HEATMAP_COLUMNS: u32 = 48;
+
+#[derive(Debug, Clone, PartialEq)]
+struct WatchOptions {
+    project: PathBuf,
+    width: u32,
+    height: u32,
+    /// Reference test name, defaults to the project file stem
+    reference: String,
+    threshold: f32,
+    backends: wgpu::Backends,
+}
+
+fn parse_options(params: &[String]) -> Result<WatchOptions, String> {
+    let mut project = None;
+    let mut width = 256;
+    let mut height = 256;
+    let mut reference = None;
+    let mut threshold = ComparisonConfig::default().threshold;
+    let mut backends = wgpu::Backends::all();
+
+    let mut params = params.iter();
+    while let Some(param) = params.next() {
+        let mut value = || {
+            params
+                .next()
+                .ok_or_else(|| format!("{} needs a value", param))
+        };
+        match param.as_str() {
+            "--size" => {
+                let size = value()?;
+                let parsed = size
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse::<u32>().ok()?, h.parse::<u32>().ok()?)))
+                    .filter(|(w, h)| *w > 0 && *h > 0);
+                (width, height) =
+                    parsed.ok_or_else(|| format!("--size needs WIDTHxHEIGHT, got '{}'", size))?;
+            }
+            "--reference" => reference = Some(value()?.clone()),
+            "--threshold" => {
+                let text = value()?;
+                threshold = text
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|t| (0.0..=1.0).contains(t))
+                    .ok_or_else(|| format!("--threshold needs 0.0 to 1.0, got '{}'", text))?;
+            }
+            "--backend" => {
+                let name = value()?;
+                backends = adapter::parse_backends(name).ok_or_else(|| {
+                    format!(
+                        "Unknown backend '{}', expected one of: {}",
+                        name,
+                        adapter::backend_input_options().join(", ")
+                    )
+                })?;
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown option '{}'", other));
+            }
+            path if project.is_none() => project = Some(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument '{}'", extra)),
+        }
+    }
+
+    let project = project.ok_or_else(|| "Missing project file".to_string())?;
+    let reference = match reference {
+        Some(name) => name,
+        None => project
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| "Cannot derive a reference name; pass --reference".to_string())?
+            .to_string(),
+    };
+    Ok(WatchOptions {
+        project,
+        width,
+        height,
+        reference,
+        threshold,
+        backends,
+    })
+}
+
+fn show_help() {
+    println!("USAGE:");
+    println!("  wgpu_playground watch <project.json> [options]");
+    println!();
+    println!("OPTIONS:");
+    println!("  --size <WxH>         Frame size (default 256x256)");
+    println!("  --reference <name>   Reference test name (default: project file name)");
+    println!("  --threshold <t>      Allowed difference, 0.0 to 1.0 (default 0.01)");
+    println!("  --backend <name>     Backend to render with (default all)");
+}
+
+/// Locate the shader file a project refers to
+///
+/// The path is tried as given, next to the project file and in the shaders
+/// directory. A missing file still resolves next to the project so that
+/// creating it is noticed.
+fn resolve_shader_path(project: &Path, file_path: &str) -> Option<PathBuf> {
+    if file_path.trim().is_empty() {
+        return None;
+    }
+    let given = PathBuf::from(file_path);
+    if given.is_absolute() {
+        return Some(given);
+    }
+    let beside_project = project
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(&given);
+    [
+        beside_project.clone(),
+        given,
+        assets::shaders_dir().join(file_path),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.is_file())
+    .or(Some(beside_project))
+}
+
+/// ANSI styling, disabled for pipes and when `NO_COLOR` is set
+#[derive(Debug, Clone, Copy)]
+struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    fn detect() -> Self {
+        Self {
+            enabled: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn pass(&self, text: &str) -> String {
+        self.paint("1;32", text)
+    }
+
+    fn fail(&self, text: &str) -> String {
+        self.paint("1;31", text)
+    }
+
+    fn warn(&self, text: &str) -> String {
+        self.paint("1;33", text)
+    }
+
+    fn dim(&self, text: &str) -> String {
+        self.paint("2", text)
+    }
+}
+
+/// Mean channel difference of each heatmap cell, in `0.0..=1.0`
+fn cell_differences(a: &RgbaImage, b: &RgbaImage, columns: u32) -> Vec<Vec<f32>> {
+    let (width, height) = a.dimensions();
+    let columns = columns.clamp(1, width);
+    // Characters are about twice as tall as wide and each shows two cells
+    // stacked with a half block, so cells are square
+    let rows = ((height as f32 / width as f32 * columns as f32).round() as u32).clamp(1, height);
+
+    (0..rows)
+        .map(|row| {
+            let y0 = row * height / rows;
+            let y1 = ((row + 1) * height / rows).max(y0 + 1);
+            (0..columns)
+                .map(|column| {
+                    let x0 = column * width / columns;
+                    let x1 = ((column + 1) * width / columns).max(x0 + 1);
+                    let mut total = 0u64;
+                    for y in y0..y1 {
+                        for x in x0..x1 {
+                            let (pa, pb) = (a.get_pixel(x, y).0, b.get_pixel(x, y).0);
+                            total += pa
+                                .iter()
+                                .zip(pb.iter())
+                                .map(|(ca, cb)| ca.abs_diff(*cb) as u64)
+                                .sum::<u64>();
+                        }
+                    }
+                    let samples = ((x1 - x0) * (y1 - y0) * 4) as f32;
+                    total as f32 / (samples * 255.0)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Black for identical cells, through yellow to red for large differences
+fn heat_color(difference: f32) -> (u8, u8, u8) {
+    if difference <= 0.0 {
+        return (30, 30, 30);
+    }
+    // Small differences matter most, so stretch the low end
+    let t = difference.sqrt().clamp(0.0, 1.0);
+    let green = (200.0 * (1.0 - t)) as u8 + 30;
+    (255, green, 0)
+}
+
+/// Render the difference between two images as terminal lines
+///
+/// With colors, each character shows two cells using a half block with
+/// 24-bit foreground and background colors; without, a character ramp.
+fn diff_heatmap(a: &RgbaImage, b: &RgbaImage, columns: u32, palette: Palette) -> Vec<String> {
+    let cells = cell_differences(a, b, columns);
+    if !palette.enabled {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+        return cells
+            .iter()
+            .step_by(2)
+            .map(|row| {
+                row.iter()
+                    .map(|d| {
+                        let t = d.sqrt().clamp(0.0, 1.0);
+                        let index = if *d > 0.0 {
+                            ((t * (RAMP.len() - 1) as f32).ceil() as usize).max(1)
+                        } else {
+                            0
+                        };
+                        RAMP[index.min(RAMP.len() - 1)] as char
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+
+    cells
+        .chunks(2)
+        .map(|pair| {
+            let mut line = String::new();
+            for (column, top) in pair[0].iter().enumerate() {
+                let (tr, tg, tb) = heat_color(*top);
+                let (br, bg, bb) = pair
+                    .get(1)
+                    .map(|bottom| heat_color(bottom[column]))
+                    .unwrap_or((0, 0, 0));
+                line.push_str(&format!(
+                    "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
+                    tr, tg, tb, br, bg, bb
+                ));
+            }
+            line.push_str("\x1b[0m");
+            line
+        })
+        .collect()
+}
+
+/// Files whose changes trigger a render
+fn watched_files(options: &WatchOptions) -> Vec<PathBuf> {
+    let mut files = vec![options.project.clone()];
+    let shader = PlaygroundState::load_from_file(&options.project)
+        .ok()
+        .and_then(|project| project.shader_editor)
+        .and_then(|editor| resolve_shader_path(&options.project, &editor.file_path));
+    files.extend(shader);
+    files
+}
+
+/// Render once and print the result; returns whether it passed
+fn render_and_compare(session: &mut Session, options: &WatchOptions, palette: Palette) -> bool {
+    let started = Instant::now();
+    if let Err(e) = session.load_project(&options.project) {
+        println!("{} {}", palette.fail("ERROR"), e);
+        return false;
+    }
+    // A shader file on disk wins over the copy saved in the project
+    if let Some(path) = watched_files(options).get(1) {
+        match std::fs::read_to_string(path) {
+            Ok(source) => session.set_shader(&source),
+            Err(e) => {
+                println!(
+                    "{} Cannot read {}: {}",
+                    palette.fail("ERROR"),
+                    path.display(),
+                    e
+                );
+                return false;
+            }
+        }
+    }
+
+    let pixels = match session.render_frame(options.width, options.height) {
+        Ok(pixels) => pixels,
+        Err(e) => {
+            println!("{} {}", palette.fail("ERROR"), e);
+            return false;
+        }
+    };
+    let Some(frame) = RgbaImage::from_raw(options.width, options.height, pixels) else {
+        println!("{} Frame has an unexpected size", palette.fail("ERROR"));
+        return false;
+    };
+    let elapsed = started.elapsed().as_secs_f64() * 1000.0;
+
+    let config = ComparisonConfig {
+        threshold: options.threshold,
+        save_diff: true,
+        update_references: false,
+    };
+    let store = ReferenceStore::default();
+    if !store.reference_path(&options.reference).exists() {
+        // Still writes the output, so it can be promoted
+        let _ = visual_regression::compare_with_reference(&frame, &options.reference, config);
+        println!(
+            "{} No reference for '{}' ({:.1} ms). Accept this frame with:",
+            palette.warn("NEW"),
+            options.reference,
+            elapsed
+        );
+        println!("    reference_manager promote {}", options.reference);
+        return false;
+    }
+
+    match visual_regression::compare_with_reference(&frame, &options.reference, config) {
+        Ok(result) if result.is_match => {
+            println!(
+                "{} {} (Δ={:.4}%, {:.1} ms)",
+                palette.pass("PASS"),
+                options.reference,
+                result.difference * 100.0,
+                elapsed
+            );
+            true
+        }
+        Ok(result) => {
+            println!(
+                "{} {} (Δ={:.4}% > {:.4}%, {:.1} ms)",
+                palette.fail("FAIL"),
+                options.reference,
+                result.difference * 100.0,
+                options.threshold * 100.0,
+                elapsed
+            );
+            if let Ok(reference) = image::open(store.reference_path(&options.reference)) {
+                for line in diff_heatmap(&frame, &reference.to_rgba8(), HEATMAP_COLUMNS, palette) {
+                    println!("    {}", line);
+                }
+            }
+            if let Some(path) = result.diff_image_path {
+                println!(
+                    "    {}",
+                    palette.dim(&format!("Diff image: {}", path.display()))
+                );
+            }
+            false
+        }
+        Err(e) => {
+            println!("{} {}", palette.fail("FAIL"), e);
+            false
+        }
+    }
+}
+
+/// Entry point of `wgpu_playground watch`
+pub fn run(params: &[String]) -> ExitCode {
+    if params.iter().any(|p| p == "--help" || p == "-h") {
+        show_help();
+        return ExitCode::SUCCESS;
+    }
+    let options = match parse_options(params) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            show_help();
+            return ExitCode::FAILURE;
+        }
+    };
+    let palette = Palette::detect();
+
+    let mut session = match Session::new(options.backends) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let info = session.adapter_info();
+    println!(
+        "Watching {} on {} ({}) - press Ctrl+C to stop",
+        options.project.display(),
+        info.name,
+        adapter::backend_to_str(&info.backend)
+    );
+
+    let assets = AssetWatcher::new()
+        .map_err(|e| log::warn!("Not watching assets: {}", e))
+        .ok();
+    let mut files = watched_files(&options);
+    let mut watcher = match FileWatcher::watch(&files) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!("Cannot watch {}: {}", options.project.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    render_and_compare(&mut session, &options, palette);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut changed: Vec<String> = watcher
+            .poll_all()
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        if let Some(assets) = &assets {
+            changed.extend(assets.poll_all().into_iter().map(|event| event.filename));
+        }
+        if changed.is_empty() {
+            continue;
+        }
+
+        thread::sleep(SETTLE_TIME);
+        watcher.poll_all();
+        if let Some(assets) = &assets {
+            assets.poll_all();
+        }
+        changed.dedup();
+        println!();
+        println!(
+            "{}",
+            palette.dim(&format!("Changed: {}", changed.join(", ")))
+        );
+
+        // The project may now point at a different shader file
+        let current = watched_files(&options);
+        if current != files {
+            match FileWatcher::watch(&current) {
+                Ok(new_watcher) => {
+                    watcher = new_watcher;
+                    files = current;
+                }
+                Err(e) => log::warn!("Keeping previous watch list: {}", e),
+            }
+        }
+        render_and_compare(&mut session, &options, palette);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let options = parse_options(&args(&[
+            "projects/fog.json",
+            "--size",
+            "640x360",
+            "--threshold",
+            "0.05",
+        ]))
+        .unwrap();
+        assert_eq!(options.project, PathBuf::from("projects/fog.json"));
+        assert_eq!((options.width, options.height), (640, 360));
+        assert_eq!(options.reference, "fog");
+        assert_eq!(options.threshold, 0.05);
+
+        let named = parse_options(&args(&["fog.json", "--reference", "fog_night"])).unwrap();
+        assert_eq!(named.reference, "fog_night");
+
+        assert!(parse_options(&args(&[])).is_err());
+        assert!(parse_options(&args(&["a.json", "b.json"])).is_err());
+        assert!(parse_options(&args(&["a.json", "--size", "640"])).is_err());
+        assert!(parse_options(&args(&["a.json", "--backend", "glide"])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_shader_path() {
+        let project = Path::new("/work/project.json");
+        assert_eq!(resolve_shader_path(project, ""), None);
+        assert_eq!(
+            resolve_shader_path(project, "/abs/fog.wgsl"),
+            Some(PathBuf::from("/abs/fog.wgsl"))
+        );
+        // Missing files resolve next to the project
+        assert_eq!(
+            resolve_shader_path(project, "missing_shader.wgsl"),
+            Some(PathBuf::from("/work/missing_shader.wgsl"))
+        );
+    }
+
+    #[test]
+    fn test_diff_heatmap() {
+        let black = RgbaImage::from_pixel(16, 16, image::Rgba([0, 0, 0, 255]));
+        let mut changed = black.clone();
+        for y in 0..8 {
+            for x in 0..8 {
+                changed.put_pixel(x, y, image::Rgba([255, 255, 255, 0]));
+            }
+        }
+
+        let plain = Palette { enabled: false };
+        let lines = diff_heatmap(&black, &changed, 8, plain);
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.chars().count() == 8));
+        assert_eq!(lines[0], "@@@@    ");
+        assert_eq!(lines[3], "        ");
+
+        let colored = diff_heatmap(&black, &black, 8, Palette { enabled: true });
+        assert_eq!(colored.len(), 4);
+        assert!(colored[0].contains("\x1b[38;2;30;30;30m"));
+    }
+}
@@ -0,0 +1,260 @@
+use wgpu_playground_core::async_compute_panel::AsyncComputePanel;
+use wgpu_playground_core::bind_group_layout_panel::BindGroupLayoutPanel;
+use wgpu_playground_core::bind_group_panel::BindGroupPanel;
+use wgpu_playground_core::buffer_inspector::BufferInspector;
+use wgpu_playground_core::buffer_panel::BufferPanel;
+use wgpu_playground_core::capability_snapshot::ProjectDescriptor;
+use wgpu_playground_core::command_recording_panel::CommandRecordingPanel;
+use wgpu_playground_core::compute::ComputePanel;
+use wgpu_playground_core::compute_dispatch_panel::ComputeDispatchPanel;
+use wgpu_playground_core::compute_pipeline_panel::ComputePipelinePanel;
+use wgpu_playground_core::debug_counters_panel::DebugCountersPanel;
+use wgpu_playground_core::device_recovery::ResourceRecreate;
+use wgpu_playground_core::draw_command_panel::DrawCommandPanel;
+use wgpu_playground_core::external_comparison_panel::ExternalComparisonPanel;
+use wgpu_playground_core::fragment_playground::FragmentPlayground;
+use wgpu_playground_core::history::History;
+use wgpu_playground_core::history_panel::HistoryPanel;
+use wgpu_playground_core::model_loader_panel::ModelLoaderPanel;
+use wgpu_playground_core::pipeline_debugger::PipelineDebugger;
+use wgpu_playground_core::render_pass_panel::RenderPassPanel;
+use wgpu_playground_core::render_targets_panel::RenderTargetsPanel;
+use wgpu_playground_core::render_pipeline_panel::RenderPipelinePanel;
+use wgpu_playground_core::rendering::RenderingPanel;
+use wgpu_playground_core::resource_inspector::ResourceInspectorPanel;
+use wgpu_playground_core::sampler_panel::SamplerPanel;
+use wgpu_playground_core::shader_translation_panel::ShaderTranslationPanel;
+use wgpu_playground_core::shader_variants_panel::ShaderVariantsPanel;
+use wgpu_playground_core::state::PlaygroundState;
+use wgpu_playground_core::texture_inspector::TextureInspector;
+use wgpu_playground_core::texture_panel::TexturePanel;
+use wgpu_playground_core::workgroup_tuner_panel::WorkgroupTunerPanel;
+
+/// One independent playground configuration, shown as a tab
+///
+/// Each workspace owns its panels and the GPU resources they create, plus
+/// its own undo history. Device-level panels (adapter, device info,
+/// console, settings, tutorials) are shared and live on the app.
+pub struct Workspace {
+    pub name: String,
+    pub rendering_panel: RenderingPanel,
+    pub compute_panel: ComputePanel,
+    pub compute_pipeline_panel: ComputePipelinePanel,
+    pub compute_dispatch_panel: ComputeDispatchPanel,
+    pub async_compute_panel: AsyncComputePanel,
+    pub workgroup_tuner_panel: WorkgroupTunerPanel,
+    pub buffer_panel: BufferPanel,
+    pub sampler_panel: SamplerPanel,
+    pub texture_panel: TexturePanel,
+    pub bind_group_panel: BindGroupPanel,
+    pub bind_group_layout_panel: BindGroupLayoutPanel,
+    pub render_pipeline_panel: RenderPipelinePanel,
+    pub model_loader_panel: ModelLoaderPanel,
+    pub draw_command_panel: DrawCommandPanel,
+    pub render_pass_panel: RenderPassPanel,
+    pub render_targets_panel: RenderTargetsPanel,
+    pub fragment_playground: FragmentPlayground,
+    pub resource_inspector_panel: ResourceInspectorPanel,
+    pub buffer_inspector: BufferInspector,
+    pub texture_inspector: TextureInspector,
+    pub pipeline_debugger: PipelineDebugger,
+    pub debug_counters_panel: DebugCountersPanel,
+    pub shader_variants_panel: ShaderVariantsPanel,
+    pub shader_translation_panel: ShaderTranslationPanel,
+    pub external_comparison_panel: ExternalComparisonPanel,
+    pub command_recording_panel: CommandRecordingPanel,
+    pub history_panel: HistoryPanel,
+    /// Undo/redo snapshots of this workspace's panel state
+    pub history: History,
+}
+
+impl Workspace {
+    pub fn new(name: impl Into<String>, device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let mut workspace = Self {
+            name: name.into(),
+            rendering_panel: RenderingPanel::new(device, queue),
+            compute_panel: ComputePanel::new(),
+            compute_pipeline_panel: ComputePipelinePanel::new(),
+            compute_dispatch_panel: ComputeDispatchPanel::new(),
+            async_compute_panel: AsyncComputePanel::new(),
+            workgroup_tuner_panel: WorkgroupTunerPanel::new(),
+            buffer_panel: BufferPanel::new(),
+            sampler_panel: SamplerPanel::new(),
+            texture_panel: TexturePanel::new(),
+            bind_group_panel: BindGroupPanel::new(),
+            bind_group_layout_panel: BindGroupLayoutPanel::new(),
+            render_pipeline_panel: RenderPipelinePanel::new(),
+            model_loader_panel: ModelLoaderPanel::new(),
+            draw_command_panel: DrawCommandPanel::new(),
+            render_pass_panel: RenderPassPanel::new(),
+            render_targets_panel: RenderTargetsPanel::new(),
+            fragment_playground: FragmentPlayground::new(),
+            resource_inspector_panel: ResourceInspectorPanel::new(),
+            buffer_inspector: BufferInspector::new(),
+            texture_inspector: TextureInspector::new(),
+            pipeline_debugger: PipelineDebugger::new(),
+            debug_counters_panel: DebugCountersPanel::new(),
+            shader_variants_panel: ShaderVariantsPanel::new(),
+            shader_translation_panel: ShaderTranslationPanel::new(),
+            external_comparison_panel: ExternalComparisonPanel::new(),
+            command_recording_panel: CommandRecordingPanel::new(),
+            history_panel: HistoryPanel::new(),
+            history: History::default(),
+        };
+        let state = workspace.export_state();
+        workspace.history.reset(&state, 0.0);
+        workspace
+    }
+
+    /// Export the panel state of this workspace
+    ///
+    /// Covers the panels in [`UNDO_SECTIONS`], which is what undo/redo
+    /// records. App-wide fields (theme, tutorial state, learning progress)
+    /// are left at their defaults.
+    ///
+    /// [`UNDO_SECTIONS`]: wgpu_playground_core::history::UNDO_SECTIONS
+    pub fn export_state(&self) -> PlaygroundState {
+        PlaygroundState {
+            buffer_panel: Some(self.buffer_panel.export_state()),
+            texture_panel: Some(self.texture_panel.export_state()),
+            sampler_panel: Some(self.sampler_panel.export_state()),
+            shader_editor: Some(self.rendering_panel.export_shader_editor_state()),
+            render_pipeline_panel: Some(self.render_pipeline_panel.export_state()),
+            compute_pipeline_panel: Some(self.compute_pipeline_panel.export_state()),
+            bind_group_panel: Some(self.bind_group_panel.export_state()),
+            bind_group_layout_panel: Some(self.bind_group_layout_panel.export_state()),
+            ..PlaygroundState::new()
+        }
+    }
+
+    /// The buffer, texture and compute workgroup descriptors configured in
+    /// this workspace, for validation against a virtual device
+    pub fn project_descriptors(&self) -> Vec<ProjectDescriptor> {
+        [
+            self.buffer_panel.project_descriptor(),
+            self.texture_panel.project_descriptor(),
+            self.compute_pipeline_panel.project_descriptor(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Import the panel parts of a state into this workspace
+    ///
+    /// The change is reported to the undo history and recorded at the end of
+    /// the frame.
+    pub fn import_state(&mut self, state: &PlaygroundState) {
+        if let Some(buffer_state) = &state.buffer_panel {
+            self.buffer_panel.import_state(buffer_state);
+        }
+        if let Some(texture_state) = &state.texture_panel {
+            self.texture_panel.import_state(texture_state);
+        }
+        if let Some(sampler_state) = &state.sampler_panel {
+            self.sampler_panel.import_state(sampler_state);
+        }
+        if let Some(shader_state) = &state.shader_editor {
+            self.rendering_panel
+                .import_shader_editor_state(shader_state);
+        }
+        if let Some(render_pipeline_state) = &state.render_pipeline_panel {
+            self.render_pipeline_panel
+                .import_state(render_pipeline_state);
+        }
+        if let Some(compute_pipeline_state) = &state.compute_pipeline_panel {
+            self.compute_pipeline_panel
+                .import_state(compute_pipeline_state);
+        }
+        if let Some(bind_group_state) = &state.bind_group_panel {
+            self.bind_group_panel.import_state(bind_group_state);
+        }
+        if let Some(bind_group_layout_state) = &state.bind_group_layout_panel {
+            self.bind_group_layout_panel
+                .import_state(bind_group_layout_state);
+        }
+        self.history.mark_edited();
+    }
+
+    /// Import a state that a project or lesson starts from
+    ///
+    /// Unlike [`Workspace::import_state`], the shader also goes to the
+    /// compute pipeline panel, which compiles its own copy.
+    pub fn import_project_state(&mut self, state: &PlaygroundState) {
+        self.import_state(state);
+        if let (Some(_), Some(shader)) = (&state.compute_pipeline_panel, &state.shader_editor) {
+            self.compute_pipeline_panel.shader_source = shader.source_code.clone();
+        }
+    }
+
+    /// Apply a state taken from the undo history
+    pub fn restore_history_state(&mut self, state: PlaygroundState) {
+        self.import_state(&state);
+        // Panels may normalize imported values, so store what they ended up
+        // with to avoid recording the restore itself as a new edit
+        let applied = self.export_state();
+        self.history.sync(&applied);
+    }
+
+    /// Step back in the undo history
+    pub fn undo(&mut self) {
+        if let Some(state) = self.history.undo() {
+            self.restore_history_state(state);
+        }
+    }
+
+    /// Step forward in the undo history
+    pub fn redo(&mut self) {
+        if let Some(state) = self.history.redo() {
+            self.restore_history_state(state);
+        }
+    }
+}
+
+impl ResourceRecreate for Workspace {
+    fn recreate_resources(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.rendering_panel.recreate_resources(device, queue);
+        self.buffer_inspector.recreate_resources(device, queue);
+        self.debug_counters_panel.recreate_resources(device, queue);
+        self.async_compute_panel.recreate_resources(device, queue);
+        self.render_targets_panel.recreate_resources(device, queue);
+        self.compute_panel.recreate_resources(device, queue);
+        self.compute_pipeline_panel.recreate_resources(device, queue);
+        self.buffer_panel.recreate_resources(device, queue);
+        self.sampler_panel.recreate_resources(device, queue);
+        self.texture_panel.recreate_resources(device, queue);
+        self.bind_group_panel.recreate_resources(device, queue);
+        self.render_pipeline_panel.recreate_resources(device, queue);
+        self.model_loader_panel.recreate_resources(device, queue);
+        self.fragment_playground.recreate_resources(device, queue);
+        self.external_comparison_panel
+            .recreate_resources(device, queue);
+    }
+}
+
+/// First "Workspace N" name not already taken
+pub fn next_workspace_name<'a>(existing: impl IntoIterator<Item = &'a str>) -> String {
+    let existing: Vec<&str> = existing.into_iter().collect();
+    (1..)
+        .map(|n| format!("Workspace {}", n))
+        .find(|name| !existing.contains(&name.as_str()))
+        .expect("unbounded range always yields a free name")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_workspace_name() {
+        assert_eq!(next_workspace_name([]), "Workspace 1");
+        assert_eq!(
+            next_workspace_name(["Workspace 1", "Reference"]),
+            "Workspace 2"
+        );
+        assert_eq!(
+            next_workspace_name(["Workspace 2", "Workspace 3"]),
+            "Workspace 1"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Python bindings for the headless testing API
+//!
+//! Wraps the headless [`Session`] and the visual regression helpers from
+//! `wgpu_playground_core`, so GPU test suites written in Python can render
+//! playground projects and compare them against the same reference images as
+//! the Rust tests. Build with `maturin develop`.
+//!
+//! GPU work runs with the GIL released.
+
+use std::path::PathBuf;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use wgpu_playground_core::adapter;
+use wgpu_playground_core::headless_session::{Session, SessionError};
+use wgpu_playground_core::visual_regression::{self, ComparisonConfig};
+
+create_exception!(
+    wgpu_playground_py,
+    PlaygroundError,
+    PyException,
+    "Base class for errors reported by the playground"
+);
+create_exception!(
+    wgpu_playground_py,
+    NoAdapterError,
+    PlaygroundError,
+    "No GPU adapter or device could be created"
+);
+create_exception!(
+    wgpu_playground_py,
+    ProjectError,
+    PlaygroundError,
+    "The project file could not be read or parsed"
+);
+create_exception!(
+    wgpu_playground_py,
+    ShaderError,
+    PlaygroundError,
+    "The shader is missing, invalid or lacks the needed entry points"
+);
+create_exception!(
+    wgpu_playground_py,
+    GpuError,
+    PlaygroundError,
+    "The device rejected a pipeline, dispatch or readback"
+);
+create_exception!(
+    wgpu_playground_py,
+    ReferenceError,
+    PlaygroundError,
+    "The visual regression reference could not be used"
+);
+
+fn to_py_err(error: SessionError) -> PyErr {
+    let message = error.to_string();
+    match error {
+        SessionError::InvalidArgument(_) | SessionError::BufferTooSmall { .. } => {
+            PyValueError::new_err(message)
+        }
+        SessionError::NoAdapter(_) => NoAdapterError::new_err(message),
+        SessionError::Project(_) => ProjectError::new_err(message),
+        SessionError::Shader(_) => ShaderError::new_err(message),
+        SessionError::Gpu(_) => GpuError::new_err(message),
+        SessionError::Reference(_) => ReferenceError::new_err(message),
+    }
+}
+
+fn parse_backends(backend: Option<&str>) -> PyResult<wgpu::Backends> {
+    match backend {
+        Some(name) => adapter::parse_backends(name).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Unknown backend '{}', expected one of: {}",
+                name,
+                adapter::backend_input_options().join(", ")
+            ))
+        }),
+        None => Ok(wgpu::Backends::all()),
+    }
+}
+
+fn rgba_image(pixels: &[u8], width: u32, height: u32) -> PyResult<image::RgbaImage> {
+    let expected = width as usize * height as usize * 4;
+    if pixels.len() != expected {
+        return Err(PyValueError::new_err(format!(
+            "Expected {} bytes of RGBA8 pixels for {}x{}, got {}",
+            expected,
+            width,
+            height,
+            pixels.len()
+        )));
+    }
+    Ok(image::RgbaImage::from_raw(width, height, pixels.to_vec()).expect("size was checked"))
+}
+
+/// Headless device plus a loaded project
+#[pyclass(name = "Session", module = "wgpu_playground_py")]
+struct PySession {
+    inner: Session,
+}
+
+#[pymethods]
+impl PySession {
+    #[new]
+    #[pyo3(signature = (backend = None))]
+    fn new(py: Python<'_>, backend: Option<&str>) -> PyResult<Self> {
+        let backends = parse_backends(backend)?;
+        let inner = py
+            .allow_threads(|| Session::new(backends))
+            .map_err(to_py_err)?;
+        Ok(Self { inner })
+    }
+
+    /// Name of the GPU adapter
+    #[getter]
+    fn adapter_name(&self) -> String {
+        self.inner.adapter_info().name.clone()
+    }
+
+    /// Backend the session runs on, e.g. "Vulkan"
+    #[getter]
+    fn backend(&self) -> &'static str {
+        adapter::backend_to_str(&self.inner.adapter_info().backend)
+    }
+
+    /// Current WGSL source, if any
+    #[getter]
+    fn shader(&self) -> Option<String> {
+        self.inner.shader_source().map(str::to_string)
+    }
+
+    /// Load a project saved by the playground
+    fn load_project(&mut self, path: PathBuf) -> PyResult<()> {
+        self.inner.load_project(&path).map_err(to_py_err)
+    }
+
+    /// Replace the session shader with WGSL source
+    fn set_shader(&mut self, source: &str) {
+        self.inner.set_shader(source);
+    }
+
+    /// Render one frame; returns `width * height * 4` bytes of sRGB RGBA8
+    fn render_frame<'py>(
+        &self,
+        py: Python<'py>,
+        width: u32,
+        height: u32,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let pixels = py
+            .allow_threads(|| self.inner.render_frame(width, height))
+            .map_err(to_py_err)?;
+        Ok(PyBytes::new(py, &pixels))
+    }
+
+    /// Dispatch a compute entry point over the storage buffer at binding 0
+    ///
+    /// Returns the first `output_len` bytes of the buffer, which defaults to
+    /// the input size.
+    #[pyo3(signature = (input, output_len = None, workgroups = (1, 1, 1), entry_point = None))]
+    fn run_compute<'py>(
+        &self,
+        py: Python<'py>,
+        input: &[u8],
+        output_len: Option<usize>,
+        workgroups: (u32, u32, u32),
+        entry_point: Option<&str>,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let output_len = output_len.unwrap_or(input.len());
+        let (x, y, z) = workgroups;
+        let output = py
+            .allow_threads(|| {
+                self.inner
+                    .run_compute(entry_point, input, output_len, [x, y, z])
+            })
+            .map_err(to_py_err)?;
+        Ok(PyBytes::new(py, &output))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Session(adapter={:?}, backend={:?})",
+            self.adapter_name(),
+            self.backend()
+        )
+    }
+}
+
+/// Outcome of [`compare_with_reference`]
+#[pyclass(
+    name = "ComparisonResult",
+    module = "wgpu_playground_py",
+    frozen,
+    get_all
+)]
+struct PyComparisonResult {
+    is_match: bool,
+    /// Difference in `0.0..=1.0`
+    difference: f32,
+    /// Diff image written on mismatch, if enabled
+    diff_image_path: Option<PathBuf>,
+}
+
+#[pymethods]
+impl PyComparisonResult {
+    fn __bool__(&self) -> bool {
+        self.is_match
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ComparisonResult(is_match={}, difference={:.6}, diff_image_path={:?})",
+            if self.is_match { "True" } else { "False" },
+            self.difference,
+            self.diff_image_path
+        )
+    }
+}
+
+/// Compare RGBA8 pixels with the stored reference image for `test_name`
+///
+/// Uses the same reference directory, diff images and HTML report as the
+/// Rust visual regression tests. Set `UPDATE_VISUAL_REFERENCES=1` to record
+/// missing references.
+#[pyfunction]
+#[pyo3(signature = (pixels, width, height, test_name, threshold = 0.01, save_diff = true))]
+fn compare_with_reference(
+    py: Python<'_>,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    test_name: &str,
+    threshold: f32,
+    save_diff: bool,
+) -> PyResult<PyComparisonResult> {
+    let image = rgba_image(pixels, width, height)?;
+    let config = ComparisonConfig {
+        threshold,
+        save_diff,
+        ..Default::default()
+    };
+    let result = py
+        .allow_threads(|| visual_regression::compare_with_reference(&image, test_name, config))
+        .map_err(|e| ReferenceError::new_err(e.to_string()))?;
+    Ok(PyComparisonResult {
+        is_match: result.is_match,
+        difference: result.difference,
+        diff_image_path: result.diff_image_path,
+    })
+}
+
+/// Difference between two RGBA8 images of the same size, in `0.0..=1.0`
+#[pyfunction]
+fn image_difference(a: &[u8], b: &[u8], width: u32, height: u32) -> PyResult<f32> {
+    let a = rgba_image(a, width, height)?;
+    let b = rgba_image(b, width, height)?;
+    visual_regression::image_difference(&a, &b).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Save RGBA8 pixels as a PNG file
+#[pyfunction]
+fn save_png(pixels: &[u8], width: u32, height: u32, path: PathBuf) -> PyResult<()> {
+    rgba_image(pixels, width, height)?
+        .save(&path)
+        .map_err(|e| PlaygroundError::new_err(format!("{}: {}", path.display(), e)))
+}
+
+/// Load a PNG file as `(pixels, width, height)` RGBA8
+#[pyfunction]
+fn load_png(py: Python<'_>, path: PathBuf) -> PyResult<(Bound<'_, PyBytes>, u32, u32)> {
+    let image = image::open(&path)
+        .map_err(|e| PlaygroundError::new_err(format!("{}: {}", path.display(), e)))?
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    Ok((PyBytes::new(py, image.as_raw()), width, height))
+}
+
+/// Render a saved project in a fresh session
+///
+/// Shorthand for creating a `Session`, loading the project and rendering one
+/// frame.
+#[pyfunction]
+#[pyo3(signature = (path, width = 256, height = 256, backend = None))]
+fn run_project(
+    py: Python<'_>,
+    path: PathBuf,
+    width: u32,
+    height: u32,
+    backend: Option<&str>,
+) -> PyResult<Bound<'_, PyBytes>> {
+    let backends = parse_backends(backend)?;
+    let pixels = py
+        .allow_threads(|| {
+            let mut session = Session::new(backends)?;
+            session.load_project(&path)?;
+            session.render_frame(width, height)
+        })
+        .map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &pixels))
+}
+
+#[pymodule]
+fn wgpu_playground_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add_class::<PySession>()?;
+    m.add_class::<PyComparisonResult>()?;
+    m.add_function(wrap_pyfunction!(compare_with_reference, m)?)?;
+    m.add_function(wrap_pyfunction!(image_difference, m)?)?;
+    m.add_function(wrap_pyfunction!(save_png, m)?)?;
+    m.add_function(wrap_pyfunction!(load_png, m)?)?;
+    m.add_function(wrap_pyfunction!(run_project, m)?)?;
+    m.add("PlaygroundError", py.get_type::<PlaygroundError>())?;
+    m.add("NoAdapterError", py.get_type::<NoAdapterError>())?;
+    m.add("ProjectError", py.get_type::<ProjectError>())?;
+    m.add("ShaderError", py.get_type::<ShaderError>())?;
+    m.add("GpuError", py.get_type::<GpuError>())?;
+    m.add("ReferenceError", py.get_type::<ReferenceError>())?;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+"""Tests for the Python bindings. Run with `maturin develop && pytest tests`."""
+
+import json
+import struct
+
+import pytest
+
+import wgpu_playground_py as wp
+
+TRIANGLE = """
+@vertex
+fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+    let x = f32(i32(i) - 1);
+    let y = f32(i32(i & 1u) * 2 - 1);
+    return vec4<f32>(x * 3.0, y * 3.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+}
+"""
+
+DOUBLE = """
+@group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    data[id.x] = data[id.x] * 2u;
+}
+"""
+
+
+@pytest.fixture(scope="module")
+def session():
+    try:
+        return wp.Session()
+    except wp.NoAdapterError as e:
+        pytest.skip(f"No GPU adapter available: {e}")
+
+
+def test_image_difference():
+    black = bytes(4 * 4 * 4)
+    assert wp.image_difference(black, black, 4, 4) == 0.0
+    assert wp.image_difference(black, bytes([255]) * 64, 4, 4) > 0.0
+    with pytest.raises(ValueError):
+        wp.image_difference(black, black[:-1], 4, 4)
+
+
+def test_png_roundtrip(tmp_path):
+    pixels = bytes(range(64))
+    path = tmp_path / "roundtrip.png"
+    wp.save_png(pixels, 4, 4, path)
+    assert wp.load_png(path) == (pixels, 4, 4)
+
+
+def test_unknown_backend():
+    with pytest.raises(ValueError, match="Unknown backend"):
+        wp.Session("glide")
+
+
+def test_render_frame(session):
+    session.set_shader(TRIANGLE)
+    pixels = session.render_frame(8, 8)
+    assert len(pixels) == 8 * 8 * 4
+    assert pixels[:4] == bytes([0, 255, 0, 255])
+
+
+def test_shader_errors(session):
+    session.set_shader("fn broken(")
+    with pytest.raises(wp.ShaderError):
+        session.render_frame(8, 8)
+
+
+def test_run_compute(session):
+    session.set_shader(DOUBLE)
+    data = struct.pack("<4I", 1, 2, 3, 4)
+    output = session.run_compute(data, workgroups=(4, 1, 1))
+    assert struct.unpack("<4I", output) == (2, 4, 6, 8)
+
+
+def test_run_project(tmp_path, session):
+    project = {"version": "1.0", "shader_editor": {"source_code": TRIANGLE, "label": "", "file_path": ""}}
+    path = tmp_path / "project.json"
+    path.write_text(json.dumps(project))
+    pixels = wp.run_project(path, width=4, height=4)
+    assert pixels[:4] == bytes([0, 255, 0, 255])
+
+    with pytest.raises(wp.ProjectError):
+        wp.run_project(tmp_path / "missing.json")
@@ -0,0 +1,57 @@
+"""Headless rendering and visual regression testing from wgpu_playground."""
+
+import os
+from typing import Optional, Tuple, Union
+
+_Path = Union[str, "os.PathLike[str]"]
+
+class PlaygroundError(Exception): ...
+class NoAdapterError(PlaygroundError): ...
+class ProjectError(PlaygroundError): ...
+class ShaderError(PlaygroundError): ...
+class GpuError(PlaygroundError): ...
+class ReferenceError(PlaygroundError): ...
+
+class Session:
+    """Headless device plus a loaded project."""
+
+    def __init__(self, backend: Optional[str] = None) -> None: ...
+    @property
+    def adapter_name(self) -> str: ...
+    @property
+    def backend(self) -> str: ...
+    @property
+    def shader(self) -> Optional[str]: ...
+    def load_project(self, path: _Path) -> None: ...
+    def set_shader(self, source: str) -> None: ...
+    def render_frame(self, width: int, height: int) -> bytes:
+        """Render one frame; returns width * height * 4 bytes of sRGB RGBA8."""
+    def run_compute(
+        self,
+        input: bytes,
+        output_len: Optional[int] = None,
+        workgroups: Tuple[int, int, int] = (1, 1, 1),
+        entry_point: Optional[str] = None,
+    ) -> bytes:
+        """Dispatch a compute entry point over the storage buffer at binding 0."""
+
+class ComparisonResult:
+    is_match: bool
+    difference: float
+    diff_image_path: Optional[str]
+    def __bool__(self) -> bool: ...
+
+def compare_with_reference(
+    pixels: bytes,
+    width: int,
+    height: int,
+    test_name: str,
+    threshold: float = 0.01,
+    save_diff: bool = True,
+) -> ComparisonResult: ...
+def image_difference(a: bytes, b: bytes, width: int, height: int) -> float: ...
+def save_png(pixels: bytes, width: int, height: int, path: _Path) -> None: ...
+def load_png(path: _Path) -> Tuple[bytes, int, int]: ...
+def run_project(
+    path: _Path, width: int = 256, height: int = 256, backend: Optional[str] = None
+) -> bytes: ...
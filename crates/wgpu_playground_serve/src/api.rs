@@ -0,0 +1,274 @@
+//! HTTP routes
+//!
+//! | Method   | Path                   | Description                          |
+//! |----------|------------------------|--------------------------------------|
+//! | `GET`    | `/health`              | Worker and queue counts              |
+//! | `POST`   | `/jobs`                | Submit a project file as the body    |
+//! | `GET`    | `/jobs`                | Status of all known jobs             |
+//! | `GET`    | `/jobs/{id}`           | Status and results of one job        |
+//! | `GET`    | `/jobs/{id}/frame.png` | The rendered frame of a finished job |
+//! | `DELETE` | `/jobs/{id}`           | Cancel a queued job or forget one    |
+//!
+//! Job options are query parameters of `POST /jobs`: `width`, `height`,
+//! `backend`, `reference` (a visual regression test name) and `threshold`.
+
+use std::io::Read;
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response};
+use wgpu_playground_core::adapter;
+use wgpu_playground_core::state::PlaygroundState;
+
+use crate::jobs::{CancelOutcome, JobQueue, JobSpec, SubmitError};
+
+/// Largest accepted frame side, in pixels
+pub const MAX_FRAME_SIZE: u32 = 8192;
+
+/// Largest accepted project file, in bytes
+pub const MAX_BODY_BYTES: u64 = 16 * 1024 * 1024;
+
+const DEFAULT_FRAME_SIZE: u32 = 256;
+const DEFAULT_THRESHOLD: f32 = 0.01;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Route {
+    Health,
+    Submit,
+    List,
+    Status(u64),
+    Frame(u64),
+    Cancel(u64),
+    NotFound,
+    MethodNotAllowed,
+}
+
+fn route(method: &Method, path: &str) -> Route {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let job_id = |segment: &str| segment.parse::<u64>().ok();
+    match (method, segments.as_slice()) {
+        (Method::Get, ["health"]) => Route::Health,
+        (Method::Post, ["jobs"]) => Route::Submit,
+        (Method::Get, ["jobs"]) => Route::List,
+        (Method::Get, ["jobs", id]) => job_id(id).map_or(Route::NotFound, Route::Status),
+        (Method::Delete, ["jobs", id]) => job_id(id).map_or(Route::NotFound, Route::Cancel),
+        (Method::Get, ["jobs", id, "frame.png"]) => {
+            job_id(id).map_or(Route::NotFound, Route::Frame)
+        }
+        (_, ["health"]) | (_, ["jobs"]) | (_, ["jobs", _]) | (_, ["jobs", _, "frame.png"]) => {
+            Route::MethodNotAllowed
+        }
+        _ => Route::NotFound,
+    }
+}
+
+/// Split `a=1&b=2` into pairs
+fn query_pairs(query: &str) -> impl Iterator<Item = (&str, &str)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+}
+
+/// Reference names become file names on the server, so keep them plain
+fn is_valid_test_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Build a job from the `POST /jobs` query string and project file body
+fn parse_job(query: &str, body: &str, default_backends: wgpu::Backends) -> Result<JobSpec, String> {
+    let project =
+        PlaygroundState::from_json(body).map_err(|e| format!("Invalid project file: {}", e))?;
+    let mut spec = JobSpec {
+        project,
+        width: DEFAULT_FRAME_SIZE,
+        height: DEFAULT_FRAME_SIZE,
+        backends: default_backends,
+        reference: None,
+        threshold: DEFAULT_THRESHOLD,
+    };
+
+    for (key, value) in query_pairs(query) {
+        match key {
+            "width" | "height" => {
+                let size = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|size| (1..=MAX_FRAME_SIZE).contains(size))
+                    .ok_or_else(|| format!("{} must be between 1 and {}", key, MAX_FRAME_SIZE))?;
+                if key == "width" {
+                    spec.width = size;
+                } else {
+                    spec.height = size;
+                }
+            }
+            "backend" => {
+                spec.backends = adapter::parse_backends(value).ok_or_else(|| {
+                    format!(
+                        "Unknown backend '{}', expected one of: {}",
+                        value,
+                        adapter::backend_input_options().join(", ")
+                    )
+                })?;
+            }
+            "reference" => {
+                if !is_valid_test_name(value) {
+                    return Err(
+                        "reference may only contain letters, digits, '_' and '-'".to_string()
+                    );
+                }
+                spec.reference = Some(value.to_string());
+            }
+            "threshold" => {
+                spec.threshold = value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|threshold| (0.0..=1.0).contains(threshold))
+                    .ok_or_else(|| "threshold must be between 0.0 and 1.0".to_string())?;
+            }
+            other => return Err(format!("Unknown option '{}'", other)),
+        }
+    }
+    Ok(spec)
+}
+
+fn json_response(status: u16, body: Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(
+            Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                .expect("static header is valid"),
+        )
+}
+
+fn error_response(status: u16, message: impl Into<String>) -> Response<std::io::Cursor<Vec<u8>>> {
+    json_response(status, json!({ "error": message.into() }))
+}
+
+/// Answer one request
+pub fn handle(
+    mut request: Request,
+    queue: &JobQueue,
+    workers: usize,
+    default_backends: wgpu::Backends,
+) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+    let response = match route(request.method(), path) {
+        Route::Health => {
+            let (queued, running) = queue.counts();
+            json_response(
+                200,
+                json!({ "status": "ok", "workers": workers, "queued": queued, "running": running }),
+            )
+        }
+        Route::Submit => {
+            let mut body = String::new();
+            let read = request
+                .as_reader()
+                .take(MAX_BODY_BYTES + 1)
+                .read_to_string(&mut body);
+            if let Err(e) = read {
+                error_response(400, format!("Could not read body: {}", e))
+            } else if body.len() as u64 > MAX_BODY_BYTES {
+                error_response(413, "Project file is too large")
+            } else {
+                match parse_job(query, &body, default_backends) {
+                    Ok(spec) => match queue.submit(spec) {
+                        Ok(id) => json_response(
+                            202,
+                            json!({ "id": id, "status_url": format!("/jobs/{}", id) }),
+                        ),
+                        Err(SubmitError::QueueFull) => {
+                            error_response(503, "Job queue is full, try again later")
+                        }
+                        Err(SubmitError::ShuttingDown) => {
+                            error_response(503, "Server is shutting down")
+                        }
+                    },
+                    Err(message) => error_response(400, message),
+                }
+            }
+        }
+        Route::List => json_response(200, json!({ "jobs": queue.list() })),
+        Route::Status(id) => match queue.report(id) {
+            Some(report) => json_response(200, report),
+            None => error_response(404, format!("No job {}", id)),
+        },
+        Route::Frame(id) => match queue.frame(id) {
+            Some(png) => Response::from_data(png).with_header(
+                Header::from_bytes(&b"Content-Type"[..], &b"image/png"[..])
+                    .expect("static header is valid"),
+            ),
+            None => error_response(404, format!("Job {} has no frame", id)),
+        },
+        Route::Cancel(id) => match queue.cancel(id) {
+            CancelOutcome::Cancelled => {
+                json_response(200, json!({ "id": id, "status": "cancelled" }))
+            }
+            CancelOutcome::Removed => json_response(200, json!({ "id": id, "status": "removed" })),
+            CancelOutcome::Running => error_response(409, format!("Job {} is running", id)),
+            CancelOutcome::NotFound => error_response(404, format!("No job {}", id)),
+        },
+        Route::NotFound => error_response(404, format!("No route for {}", path)),
+        Route::MethodNotAllowed => error_response(405, "Method not allowed"),
+    };
+
+    if let Err(e) = request.respond(response) {
+        log::warn!("Failed to send response: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes() {
+        assert_eq!(route(&Method::Get, "/health"), Route::Health);
+        assert_eq!(route(&Method::Post, "/jobs"), Route::Submit);
+        assert_eq!(route(&Method::Get, "/jobs/"), Route::List);
+        assert_eq!(route(&Method::Get, "/jobs/7"), Route::Status(7));
+        assert_eq!(route(&Method::Get, "/jobs/7/frame.png"), Route::Frame(7));
+        assert_eq!(route(&Method::Delete, "/jobs/7"), Route::Cancel(7));
+        assert_eq!(route(&Method::Get, "/jobs/abc"), Route::NotFound);
+        assert_eq!(route(&Method::Put, "/jobs/7"), Route::MethodNotAllowed);
+        assert_eq!(route(&Method::Get, "/other"), Route::NotFound);
+    }
+
+    #[test]
+    fn test_parse_job() {
+        let body = PlaygroundState::default().to_json().unwrap();
+        let spec = parse_job(
+            "width=640&height=480&backend=vulkan&reference=triangle_red&threshold=0.05",
+            &body,
+            wgpu::Backends::all(),
+        )
+        .unwrap();
+        assert_eq!((spec.width, spec.height), (640, 480));
+        assert_eq!(spec.backends, wgpu::Backends::VULKAN);
+        assert_eq!(spec.reference.as_deref(), Some("triangle_red"));
+        assert_eq!(spec.threshold, 0.05);
+
+        let defaults = parse_job("", &body, wgpu::Backends::GL).unwrap();
+        assert_eq!(defaults.width, DEFAULT_FRAME_SIZE);
+        assert_eq!(defaults.backends, wgpu::Backends::GL);
+        assert!(defaults.reference.is_none());
+    }
+
+    #[test]
+    fn test_parse_job_rejects_bad_input() {
+        let body = PlaygroundState::default().to_json().unwrap();
+        let all = wgpu::Backends::all();
+        assert!(parse_job("", "not json", all).is_err());
+        assert!(parse_job("width=0", &body, all).is_err());
+        assert!(parse_job("height=100000", &body, all).is_err());
+        assert!(parse_job("backend=glide", &body, all).is_err());
+        assert!(parse_job("reference=../../etc/passwd", &body, all).is_err());
+        assert!(parse_job("threshold=2", &body, all).is_err());
+        assert!(parse_job("frames=3", &body, all).is_err());
+    }
+}
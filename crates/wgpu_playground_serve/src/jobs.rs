@@ -0,0 +1,464 @@
+//! Job queue and workers
+//!
+//! Submitted jobs wait in a FIFO queue until a worker picks them up. Each
+//! job runs on a freshly created device that is dropped when the job ends,
+//! so a job that loses its device, leaks resources or panics cannot affect
+//! the jobs after it.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Cursor;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Instant;
+
+use serde_json::{json, Value};
+use wgpu_playground_core::adapter;
+use wgpu_playground_core::headless_session::Session;
+use wgpu_playground_core::state::PlaygroundState;
+use wgpu_playground_core::visual_regression::{self, ComparisonConfig};
+
+/// What to run for one job
+#[derive(Debug, Clone)]
+pub struct JobSpec {
+    pub project: PlaygroundState,
+    pub width: u32,
+    pub height: u32,
+    pub backends: wgpu::Backends,
+    /// Visual regression test to compare the frame against
+    pub reference: Option<String>,
+    /// Allowed difference for the regression check, in `0.0..=1.0`
+    pub threshold: f32,
+}
+
+/// Visual regression outcome of a finished job
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegressionOutcome {
+    pub test_name: String,
+    pub passed: bool,
+    pub difference: f32,
+}
+
+/// Everything a successful job produced
+#[derive(Debug, Clone)]
+pub struct JobOutput {
+    /// Adapter name and backend the job ran on
+    pub adapter: String,
+    pub render_ms: f64,
+    /// The rendered frame, PNG encoded
+    pub png: Vec<u8>,
+    pub regression: Option<RegressionOutcome>,
+}
+
+#[derive(Debug, Clone)]
+pub enum JobState {
+    Queued,
+    Running { worker: usize, started: Instant },
+    Done(JobOutput),
+    Failed(String),
+    Cancelled,
+}
+
+impl JobState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobState::Queued => "queued",
+            JobState::Running { .. } => "running",
+            JobState::Done(_) => "done",
+            JobState::Failed(_) => "failed",
+            JobState::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobState::Done(_) | JobState::Failed(_) | JobState::Cancelled
+        )
+    }
+}
+
+struct Job {
+    spec: JobSpec,
+    state: JobState,
+    submitted: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitError {
+    /// `max_queued` jobs are already waiting
+    QueueFull,
+    /// The queue no longer accepts jobs
+    ShuttingDown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelOutcome {
+    /// A queued job was cancelled
+    Cancelled,
+    /// A finished job was removed
+    Removed,
+    /// The job is running and cannot be interrupted
+    Running,
+    NotFound,
+}
+
+#[derive(Default)]
+struct Jobs {
+    next_id: u64,
+    pending: VecDeque<u64>,
+    jobs: BTreeMap<u64, Job>,
+    shutdown: bool,
+}
+
+/// Queue shared by the HTTP handler and the workers
+pub struct JobQueue {
+    jobs: Mutex<Jobs>,
+    ready: Condvar,
+    max_queued: usize,
+    /// Finished jobs kept for retrieval before the oldest are dropped
+    keep_finished: usize,
+}
+
+impl JobQueue {
+    pub fn new(max_queued: usize, keep_finished: usize) -> Self {
+        Self {
+            jobs: Mutex::new(Jobs {
+                next_id: 1,
+                ..Default::default()
+            }),
+            ready: Condvar::new(),
+            max_queued: max_queued.max(1),
+            keep_finished,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Jobs> {
+        // A worker panic is caught before it can poison the lock, but the
+        // queue itself stays consistent even if one slips through
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue a job, returning its id
+    pub fn submit(&self, spec: JobSpec) -> Result<u64, SubmitError> {
+        let mut jobs = self.lock();
+        if jobs.shutdown {
+            return Err(SubmitError::ShuttingDown);
+        }
+        if jobs.pending.len() >= self.max_queued {
+            return Err(SubmitError::QueueFull);
+        }
+        let id = jobs.next_id;
+        jobs.next_id += 1;
+        jobs.jobs.insert(
+            id,
+            Job {
+                spec,
+                state: JobState::Queued,
+                submitted: Instant::now(),
+            },
+        );
+        jobs.pending.push_back(id);
+        self.ready.notify_one();
+        Ok(id)
+    }
+
+    /// Block until a job is available and mark it as running
+    ///
+    /// Returns `None` once [`Self::shutdown`] was called.
+    pub fn next(&self, worker: usize) -> Option<(u64, JobSpec)> {
+        let mut jobs = self.lock();
+        loop {
+            if jobs.shutdown {
+                return None;
+            }
+            if let Some(id) = jobs.pending.pop_front() {
+                let Some(job) = jobs.jobs.get_mut(&id) else {
+                    continue;
+                };
+                job.state = JobState::Running {
+                    worker,
+                    started: Instant::now(),
+                };
+                return Some((id, job.spec.clone()));
+            }
+            jobs = self.ready.wait(jobs).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Store the result of a running job
+    pub fn finish(&self, id: u64, result: Result<JobOutput, String>) {
+        let mut jobs = self.lock();
+        if let Some(job) = jobs.jobs.get_mut(&id) {
+            job.state = match result {
+                Ok(output) => JobState::Done(output),
+                Err(error) => JobState::Failed(error),
+            };
+        }
+        self.evict_finished(&mut jobs);
+    }
+
+    /// Cancel a queued job or forget a finished one
+    pub fn cancel(&self, id: u64) -> CancelOutcome {
+        let mut guard = self.lock();
+        let jobs = &mut *guard;
+        let Some(job) = jobs.jobs.get_mut(&id) else {
+            return CancelOutcome::NotFound;
+        };
+        match job.state {
+            JobState::Queued => {
+                job.state = JobState::Cancelled;
+                jobs.pending.retain(|pending| *pending != id);
+                self.evict_finished(jobs);
+                CancelOutcome::Cancelled
+            }
+            JobState::Running { .. } => CancelOutcome::Running,
+            _ => {
+                jobs.jobs.remove(&id);
+                CancelOutcome::Removed
+            }
+        }
+    }
+
+    fn evict_finished(&self, jobs: &mut Jobs) {
+        let finished: Vec<u64> = jobs
+            .jobs
+            .iter()
+            .filter(|(_, job)| job.state.is_finished())
+            .map(|(id, _)| *id)
+            .collect();
+        let excess = finished.len().saturating_sub(self.keep_finished);
+        for id in &finished[..excess] {
+            jobs.jobs.remove(id);
+        }
+    }
+
+    /// Stop accepting jobs and wake idle workers so they exit
+    pub fn shutdown(&self) {
+        self.lock().shutdown = true;
+        self.ready.notify_all();
+    }
+
+    /// JSON status of one job
+    pub fn report(&self, id: u64) -> Option<Value> {
+        let jobs = self.lock();
+        let job = jobs.jobs.get(&id)?;
+        let position = jobs.pending.iter().position(|pending| *pending == id);
+        Some(job_report(id, job, position))
+    }
+
+    /// JSON status of every known job, oldest first
+    pub fn list(&self) -> Vec<Value> {
+        let jobs = self.lock();
+        jobs.jobs
+            .iter()
+            .map(|(id, job)| {
+                let position = jobs.pending.iter().position(|pending| pending == id);
+                job_report(*id, job, position)
+            })
+            .collect()
+    }
+
+    /// PNG of a finished job's frame
+    pub fn frame(&self, id: u64) -> Option<Vec<u8>> {
+        match &self.lock().jobs.get(&id)?.state {
+            JobState::Done(output) => Some(output.png.clone()),
+            _ => None,
+        }
+    }
+
+    /// `(queued, running)` job counts
+    pub fn counts(&self) -> (usize, usize) {
+        let jobs = self.lock();
+        let running = jobs
+            .jobs
+            .values()
+            .filter(|job| matches!(job.state, JobState::Running { .. }))
+            .count();
+        (jobs.pending.len(), running)
+    }
+}
+
+fn job_report(id: u64, job: &Job, queue_position: Option<usize>) -> Value {
+    let mut report = json!({
+        "id": id,
+        "status": job.state.label(),
+        "width": job.spec.width,
+        "height": job.spec.height,
+        "reference": job.spec.reference,
+        "age_seconds": job.submitted.elapsed().as_secs_f64(),
+    });
+    match &job.state {
+        JobState::Queued => {
+            report["queue_position"] = json!(queue_position);
+        }
+        JobState::Running { worker, started } => {
+            report["worker"] = json!(worker);
+            report["running_seconds"] = json!(started.elapsed().as_secs_f64());
+        }
+        JobState::Done(output) => {
+            report["adapter"] = json!(output.adapter);
+            report["render_ms"] = json!(output.render_ms);
+            report["frame_url"] = json!(format!("/jobs/{}/frame.png", id));
+            if let Some(regression) = &output.regression {
+                report["regression"] = json!({
+                    "test": regression.test_name,
+                    "passed": regression.passed,
+                    "difference": regression.difference,
+                });
+            }
+        }
+        JobState::Failed(error) => {
+            report["error"] = json!(error);
+        }
+        JobState::Cancelled => {}
+    }
+    report
+}
+
+/// Run jobs until the queue shuts down
+pub fn run_worker(queue: &JobQueue, worker: usize) {
+    while let Some((id, spec)) = queue.next(worker) {
+        log::info!(
+            "Worker {} running job {} ({}x{})",
+            worker,
+            id,
+            spec.width,
+            spec.height
+        );
+        let result = panic::catch_unwind(AssertUnwindSafe(|| execute(&spec)))
+            .unwrap_or_else(|_| Err("Job panicked; its device was discarded".to_string()));
+        match &result {
+            Ok(_) => log::info!("Job {} done", id),
+            Err(error) => log::warn!("Job {} failed: {}", id, error),
+        }
+        queue.finish(id, result);
+    }
+}
+
+/// Render a job on its own device and run its regression check
+fn execute(spec: &JobSpec) -> Result<JobOutput, String> {
+    let mut session = Session::new(spec.backends).map_err(|e| e.to_string())?;
+    let info = session.adapter_info();
+    let adapter = format!("{} ({})", info.name, adapter::backend_to_str(&info.backend));
+    session.set_project(spec.project.clone());
+
+    let started = Instant::now();
+    let pixels = session
+        .render_frame(spec.width, spec.height)
+        .map_err(|e| e.to_string())?;
+    let render_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let image = image::RgbaImage::from_raw(spec.width, spec.height, pixels)
+        .ok_or_else(|| "Frame has an unexpected size".to_string())?;
+    let regression = match &spec.reference {
+        Some(test_name) => {
+            let config = ComparisonConfig {
+                threshold: spec.threshold,
+                save_diff: true,
+                // References are only ever promoted by the machine's owner
+                update_references: false,
+            };
+            let result = visual_regression::compare_with_reference(&image, test_name, config)
+                .map_err(|e| e.to_string())?;
+            Some(RegressionOutcome {
+                test_name: test_name.clone(),
+                passed: result.is_match,
+                difference: result.difference,
+            })
+        }
+        None => None,
+    };
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode frame: {}", e))?;
+
+    Ok(JobOutput {
+        adapter,
+        render_ms,
+        png,
+        regression,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> JobSpec {
+        JobSpec {
+            project: PlaygroundState::default(),
+            width: 4,
+            height: 4,
+            backends: wgpu::Backends::all(),
+            reference: None,
+            threshold: 0.01,
+        }
+    }
+
+    fn output() -> JobOutput {
+        JobOutput {
+            adapter: "Test".to_string(),
+            render_ms: 1.0,
+            png: vec![1, 2, 3],
+            regression: None,
+        }
+    }
+
+    #[test]
+    fn test_jobs_run_in_order() {
+        let queue = JobQueue::new(8, 8);
+        let first = queue.submit(spec()).unwrap();
+        let second = queue.submit(spec()).unwrap();
+        assert_eq!(queue.report(second).unwrap()["queue_position"], 1);
+
+        let (id, _) = queue.next(0).unwrap();
+        assert_eq!(id, first);
+        assert_eq!(queue.counts(), (1, 1));
+        assert_eq!(queue.report(first).unwrap()["status"], "running");
+
+        queue.finish(first, Ok(output()));
+        assert_eq!(queue.report(first).unwrap()["status"], "done");
+        assert_eq!(queue.frame(first), Some(vec![1, 2, 3]));
+        assert_eq!(queue.frame(second), None);
+    }
+
+    #[test]
+    fn test_queue_limit() {
+        let queue = JobQueue::new(1, 8);
+        queue.submit(spec()).unwrap();
+        assert_eq!(queue.submit(spec()), Err(SubmitError::QueueFull));
+
+        queue.shutdown();
+        assert!(queue.next(0).is_none());
+        assert_eq!(queue.submit(spec()), Err(SubmitError::ShuttingDown));
+    }
+
+    #[test]
+    fn test_cancel() {
+        let queue = JobQueue::new(8, 8);
+        let queued = queue.submit(spec()).unwrap();
+        let running = queue.submit(spec()).unwrap();
+        assert_eq!(queue.cancel(queued), CancelOutcome::Cancelled);
+        assert_eq!(queue.next(0).unwrap().0, running);
+        assert_eq!(queue.cancel(running), CancelOutcome::Running);
+
+        queue.finish(running, Err("boom".to_string()));
+        assert_eq!(queue.report(running).unwrap()["error"], "boom");
+        assert_eq!(queue.cancel(running), CancelOutcome::Removed);
+        assert_eq!(queue.cancel(running), CancelOutcome::NotFound);
+    }
+
+    #[test]
+    fn test_old_finished_jobs_are_dropped() {
+        let queue = JobQueue::new(8, 2);
+        for _ in 0..3 {
+            let id = queue.submit(spec()).unwrap();
+            queue.next(0).unwrap();
+            queue.finish(id, Ok(output()));
+        }
+        let ids: Vec<_> = queue.list().iter().map(|job| job["id"].clone()).collect();
+        assert_eq!(ids, vec![json!(2), json!(3)]);
+    }
+}
@@ -0,0 +1,164 @@
+//! Render service - shares a machine's GPUs over HTTP
+//!
+//! Team members submit playground project files and get back rendered
+//! frames and visual regression results, so a lab machine with interesting
+//! GPUs can run everyone's checks. Jobs are queued and each one runs on its
+//! own device. See [`api`] for the routes.
+
+use std::env;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::thread;
+
+use wgpu_playground_core::adapter;
+
+mod api;
+mod jobs;
+
+use jobs::JobQueue;
+
+struct Options {
+    addr: String,
+    workers: usize,
+    backends: wgpu::Backends,
+    max_queued: usize,
+    keep_finished: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            addr: "127.0.0.1:8080".to_string(),
+            workers: 1,
+            backends: wgpu::Backends::all(),
+            max_queued: 64,
+            keep_finished: 256,
+        }
+    }
+}
+
+fn parse_options(params: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut params = params.iter();
+    while let Some(flag) = params.next() {
+        let mut value = || {
+            params
+                .next()
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+        let count = |value: &str| {
+            value
+                .parse::<usize>()
+                .ok()
+                .filter(|count| *count > 0)
+                .ok_or_else(|| format!("{} needs a positive number, got '{}'", flag, value))
+        };
+        match flag.as_str() {
+            "--addr" => options.addr = value()?.clone(),
+            "--workers" => options.workers = count(value()?)?,
+            "--max-queue" => options.max_queued = count(value()?)?,
+            "--keep" => options.keep_finished = count(value()?)?,
+            "--backend" => {
+                let name = value()?;
+                options.backends = adapter::parse_backends(name).ok_or_else(|| {
+                    format!(
+                        "Unknown backend '{}', expected one of: {}",
+                        name,
+                        adapter::backend_input_options().join(", ")
+                    )
+                })?;
+            }
+            other => return Err(format!("Unknown option '{}'", other)),
+        }
+    }
+    Ok(options)
+}
+
+fn show_help() {
+    println!("wgpu_playground render service");
+    println!();
+    println!("USAGE:");
+    println!("  wgpu_playground_serve [options]");
+    println!();
+    println!("OPTIONS:");
+    println!("  --addr <host:port>   Address to listen on (default 127.0.0.1:8080)");
+    println!("  --workers <n>        Jobs run in parallel, one device each (default 1)");
+    println!("  --backend <name>     Default backend for jobs (default all)");
+    println!("  --max-queue <n>      Queued jobs before submissions are refused (default 64)");
+    println!("  --keep <n>           Finished jobs kept for retrieval (default 256)");
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        show_help();
+        return ExitCode::SUCCESS;
+    }
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            show_help();
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let server = match tiny_http::Server::http(&options.addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Cannot listen on {}: {}", options.addr, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let queue = Arc::new(JobQueue::new(options.max_queued, options.keep_finished));
+    for worker in 0..options.workers {
+        let queue = Arc::clone(&queue);
+        thread::Builder::new()
+            .name(format!("render-worker-{}", worker))
+            .spawn(move || jobs::run_worker(&queue, worker))
+            .expect("failed to spawn worker thread");
+    }
+
+    println!(
+        "Serving on http://{} with {} worker(s)",
+        options.addr, options.workers
+    );
+    for request in server.incoming_requests() {
+        api::handle(request, &queue, options.workers, options.backends);
+    }
+    queue.shutdown();
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let options = parse_options(&args(&[
+            "--addr",
+            "0.0.0.0:9000",
+            "--workers",
+            "2",
+            "--backend",
+            "vulkan",
+        ]))
+        .unwrap();
+        assert_eq!(options.addr, "0.0.0.0:9000");
+        assert_eq!(options.workers, 2);
+        assert_eq!(options.backends, wgpu::Backends::VULKAN);
+        assert_eq!(options.max_queued, 64);
+
+        assert!(parse_options(&args(&["--workers", "0"])).is_err());
+        assert!(parse_options(&args(&["--workers"])).is_err());
+        assert!(parse_options(&args(&["--verbose"])).is_err());
+    }
+}